@@ -9,7 +9,11 @@ pub async fn list_all() -> Result<
     axum::http::StatusCode,
 > {
     match a006_connection_mp::service::list_all().await {
-        Ok(v) => Ok(Json(v)),
+        Ok(v) => Ok(Json(
+            v.into_iter()
+                .map(a006_connection_mp::service::masked)
+                .collect(),
+        )),
         Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
@@ -26,7 +30,7 @@ pub async fn get_by_id(
         Err(_) => return Err(axum::http::StatusCode::BAD_REQUEST),
     };
     match a006_connection_mp::service::get_by_id(uuid).await {
-        Ok(Some(v)) => Ok(Json(v)),
+        Ok(Some(v)) => Ok(Json(a006_connection_mp::service::masked(v))),
         Ok(None) => Err(axum::http::StatusCode::NOT_FOUND),
         Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
     }
@@ -1,9 +1,14 @@
 use axum::{extract::Path, extract::Query, Json};
-use contracts::domain::a007_marketplace_product::aggregate::MarketplaceProductListItemDto;
+use contracts::domain::a007_marketplace_product::aggregate::{
+    AbcClass, CrossMappingResponse, ExplainSalesAnomalyRequest, MarketplaceProductListItemDto,
+    PriceHistoryResponse, ResolveBarcodeConflictRequest, ResolveBarcodeConflictResponse,
+    SalesAnomalyDirection, SalesAnomalyListResponse, XyzClass,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use crate::domain::a007_marketplace_product;
+use crate::system::auth::extractor::CurrentUser;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct PaginatedMarketplaceProductResponse {
@@ -19,6 +24,9 @@ pub struct ListMarketplaceProductsQuery {
     pub marketplace_ref: Option<String>,
     pub connection_mp_ref: Option<String>,
     pub problems_only: Option<bool>,
+    /// Класс ABC/XYZ последнего рассчитанного квартала: `A`/`B`/`C`, `X`/`Y`/`Z`.
+    pub abc_class: Option<String>,
+    pub xyz_class: Option<String>,
     pub search: Option<String>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
@@ -40,6 +48,8 @@ pub async fn list_paginated(
         marketplace_ref: query.marketplace_ref,
         connection_mp_ref: query.connection_mp_ref,
         problems_only: query.problems_only.unwrap_or(false),
+        abc_class: query.abc_class.as_deref().and_then(AbcClass::from_code),
+        xyz_class: query.xyz_class.as_deref().and_then(XyzClass::from_code),
         search: query.search,
         sort_by: query.sort_by.unwrap_or_else(|| "code".to_string()),
         sort_desc: query.sort_desc.unwrap_or(false),
@@ -55,10 +65,38 @@ pub async fn list_paginated(
                 0
             };
 
+            let alert_keys: Vec<(String, String)> = result
+                .items
+                .iter()
+                .map(|p| (p.connection_mp_ref.clone(), p.marketplace_sku.clone()))
+                .collect();
+            let mut alerts = a007_marketplace_product::stock_alerts::badges_for(&alert_keys)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load stock alerts: {}", e);
+                    Default::default()
+                });
+
+            let product_ids: Vec<String> = result
+                .items
+                .iter()
+                .map(|p| p.base.id.0.to_string())
+                .collect();
+            let mut abc_xyz_labels =
+                a007_marketplace_product::abc_xyz::labels_for_products(&product_ids)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Failed to load ABC/XYZ classes: {}", e);
+                        Default::default()
+                    });
+
             let items = result
                 .items
                 .into_iter()
                 .map(|p| MarketplaceProductListItemDto {
+                    stock_alert: alerts
+                        .remove(&(p.connection_mp_ref.clone(), p.marketplace_sku.clone())),
+                    abc_xyz: abc_xyz_labels.remove(&p.base.id.0.to_string()),
                     id: p.base.id.0.to_string(),
                     code: p.base.code,
                     description: p.base.description,
@@ -162,3 +200,137 @@ pub async fn insert_test_data() -> axum::http::StatusCode {
         }
     }
 }
+
+#[derive(Debug, Deserialize)]
+pub struct SalesAnomaliesQuery {
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub connection_mp_ref: Option<String>,
+    /// `spike` | `drop`
+    pub direction: Option<String>,
+    /// `open` | `explained`; пусто — все.
+    pub status: Option<String>,
+    pub totals_only: Option<bool>,
+}
+
+/// GET /api/a007/sales-anomalies
+/// Аномалии дневных продаж (task028) для разбора.
+pub async fn list_sales_anomalies(
+    Query(query): Query<SalesAnomaliesQuery>,
+) -> Result<Json<SalesAnomalyListResponse>, axum::http::StatusCode> {
+    use a007_marketplace_product::sales_anomalies::{self, SalesAnomalyListQuery};
+
+    let list_query = SalesAnomalyListQuery {
+        date_from: query.date_from,
+        date_to: query.date_to,
+        connection_mp_ref: query.connection_mp_ref,
+        direction: query
+            .direction
+            .as_deref()
+            .and_then(SalesAnomalyDirection::from_code),
+        explained: match query.status.as_deref() {
+            Some("open") => Some(false),
+            Some("explained") => Some(true),
+            _ => None,
+        },
+        totals_only: query.totals_only.unwrap_or(false),
+    };
+    sales_anomalies::list(&list_query)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("Failed to list sales anomalies: {}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// POST /api/a007/sales-anomalies/:id/explain
+/// Пометить аномалию объяснённой; пояснение обязательно.
+pub async fn explain_sales_anomaly(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+    Json(request): Json<ExplainSalesAnomalyRequest>,
+) -> Result<(), axum::http::StatusCode> {
+    let explanation = request.explanation.trim();
+    if explanation.is_empty() {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+    match a007_marketplace_product::sales_anomalies::explain(&id, explanation, &claims.username)
+        .await
+    {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(axum::http::StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to explain sales anomaly {}: {}", id, e);
+            Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/a007/cross-mapping
+/// Группы товаров разных маркетплейсов по номенклатуре и конфликты штрихкодов.
+pub async fn cross_mapping() -> Result<Json<CrossMappingResponse>, axum::http::StatusCode> {
+    a007_marketplace_product::cross_mapping::overview()
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("Failed to build cross-marketplace mapping: {}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// POST /api/a007/cross-mapping/resolve
+/// Привязать товары со штрихкодом к выбранной номенклатуре.
+pub async fn resolve_cross_mapping_conflict(
+    Json(request): Json<ResolveBarcodeConflictRequest>,
+) -> Result<Json<ResolveBarcodeConflictResponse>, axum::http::StatusCode> {
+    let barcode = request.barcode.trim();
+    if barcode.is_empty() || request.nomenclature_ref.trim().is_empty() {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+    match a007_marketplace_product::cross_mapping::resolve_conflict(
+        barcode,
+        request.nomenclature_ref.trim(),
+    )
+    .await
+    {
+        Ok(Some(response)) => Ok(Json(response)),
+        Ok(None) => Err(axum::http::StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to resolve barcode conflict {}: {}", barcode, e);
+            Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PriceHistoryQuery {
+    /// `YYYY-MM-DD`; по умолчанию — 180 дней до `date_to`.
+    pub date_from: Option<String>,
+    /// `YYYY-MM-DD`; по умолчанию — сегодня.
+    pub date_to: Option<String>,
+}
+
+/// GET /api/a007/marketplace-product/:id/price-history
+/// Цены товара WB по дням (заказы и продажи): до скидок, со скидкой, СПП, цена покупателя.
+pub async fn price_history(
+    Path(id): Path<String>,
+    Query(query): Query<PriceHistoryQuery>,
+) -> Result<Json<PriceHistoryResponse>, axum::http::StatusCode> {
+    let today = chrono::Utc::now().date_naive();
+    let date_to = query
+        .date_to
+        .unwrap_or_else(|| today.format("%Y-%m-%d").to_string());
+    let date_from = query.date_from.unwrap_or_else(|| {
+        (today - chrono::Duration::days(180))
+            .format("%Y-%m-%d")
+            .to_string()
+    });
+    a007_marketplace_product::price_history::load(&id, &date_from, &date_to)
+        .await
+        .map(|points| Json(PriceHistoryResponse { points }))
+        .map_err(|e| {
+            tracing::error!("Failed to load price history for {}: {}", id, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
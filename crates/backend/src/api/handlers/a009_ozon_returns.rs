@@ -1,20 +1,41 @@
 use axum::{extract::Path, Json};
+use contracts::shared::access::FinanceFields;
 use serde_json::json;
 use uuid::Uuid;
 
 use crate::domain::a009_ozon_returns;
+use crate::system::access::resolver::can_view_finance;
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::org_context::ActiveOrganization;
+
+async fn finance_access(
+    claims: &contracts::system::auth::TokenClaims,
+) -> Result<bool, axum::http::StatusCode> {
+    can_view_finance(claims).await.map_err(|e| {
+        tracing::error!("Failed to resolve finance access: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
 
 /// GET /api/ozon_returns
-pub async fn list_all() -> Result<
+pub async fn list_all(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+) -> Result<
     Json<Vec<contracts::domain::a009_ozon_returns::aggregate::OzonReturnsListDto>>,
     axum::http::StatusCode,
 > {
+    let show_finance = finance_access(&claims).await?;
     match a009_ozon_returns::service::list_all().await {
         Ok(aggregates) => {
-            let list_dtos: Vec<_> = aggregates
+            let mut list_dtos: Vec<_> = aggregates
                 .into_iter()
+                .filter(|agg| active_org.allows(&agg.organization_id))
                 .map(|agg| agg.to_list_dto())
                 .collect();
+            if !show_finance {
+                list_dtos.iter_mut().for_each(FinanceFields::mask_finance);
+            }
             Ok(Json(list_dtos))
         }
         Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
@@ -23,6 +44,8 @@ pub async fn list_all() -> Result<
 
 /// GET /api/ozon_returns/:id
 pub async fn get_by_id(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
 ) -> Result<
     Json<contracts::domain::a009_ozon_returns::aggregate::OzonReturnsDetailDto>,
@@ -33,19 +56,47 @@ pub async fn get_by_id(
         Err(_) => return Err(axum::http::StatusCode::BAD_REQUEST),
     };
     match a009_ozon_returns::service::get_by_id(uuid).await {
-        Ok(Some(v)) => {
-            let detail_dto = v.to_detail_dto();
+        Ok(Some(v)) if active_org.allows(&v.organization_id) => {
+            let mut detail_dto = v.to_detail_dto();
+            if !finance_access(&claims).await? {
+                detail_dto.mask_finance();
+            }
             Ok(Json(detail_dto))
         }
-        Ok(None) => Err(axum::http::StatusCode::NOT_FOUND),
+        Ok(_) => Err(axum::http::StatusCode::NOT_FOUND),
         Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
 
+/// Возврат есть в текущей организации; возврат другой организации — как
+/// несуществующий.
+async fn ensure_visible(
+    active_org: &ActiveOrganization,
+    uuid: Uuid,
+) -> Result<(), axum::http::StatusCode> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    a009_ozon_returns::service::get_by_id(uuid)
+        .await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?
+        .filter(|v| active_org.allows(&v.organization_id))
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    Ok(())
+}
+
 /// POST /api/ozon_returns
 pub async fn upsert(
+    active_org: ActiveOrganization,
     Json(dto): Json<contracts::domain::a009_ozon_returns::aggregate::OzonReturnsDto>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    // Документ нельзя ни перенести в чужую организацию, ни изменить чужой
+    if !active_org.allows(&dto.organization_id) {
+        return Err(axum::http::StatusCode::FORBIDDEN);
+    }
+    if let Some(uuid) = dto.id.as_deref().and_then(|id| Uuid::parse_str(id).ok()) {
+        ensure_visible(&active_org, uuid).await?;
+    }
     let result = if dto.id.is_some() {
         a009_ozon_returns::service::update(dto)
             .await
@@ -65,11 +116,15 @@ pub async fn upsert(
 }
 
 /// DELETE /api/ozon_returns/:id
-pub async fn delete(Path(id): Path<String>) -> Result<(), axum::http::StatusCode> {
+pub async fn delete(
+    active_org: ActiveOrganization,
+    Path(id): Path<String>,
+) -> Result<(), axum::http::StatusCode> {
     let uuid = match Uuid::parse_str(&id) {
         Ok(uuid) => uuid,
         Err(_) => return Err(axum::http::StatusCode::BAD_REQUEST),
     };
+    ensure_visible(&active_org, uuid).await?;
     match a009_ozon_returns::service::delete(uuid).await {
         Ok(true) => Ok(()),
         Ok(false) => Err(axum::http::StatusCode::NOT_FOUND),
@@ -79,9 +134,11 @@ pub async fn delete(Path(id): Path<String>) -> Result<(), axum::http::StatusCode
 
 /// Handler для проведения документа возврата
 pub async fn post_ozon_return(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    ensure_visible(&active_org, uuid).await?;
 
     a009_ozon_returns::posting::post_document(uuid)
         .await
@@ -95,9 +152,11 @@ pub async fn post_ozon_return(
 
 /// Handler для отмены проведения документа возврата
 pub async fn unpost_ozon_return(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    ensure_visible(&active_org, uuid).await?;
 
     a009_ozon_returns::posting::unpost_document(uuid)
         .await
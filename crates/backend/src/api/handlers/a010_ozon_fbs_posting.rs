@@ -2,45 +2,99 @@ use axum::{extract::Query, Json};
 use chrono::NaiveDate;
 use contracts::domain::a010_ozon_fbs_posting::aggregate::OzonFbsPosting;
 use contracts::domain::common::AggregateId;
+use contracts::shared::access::FinanceFields;
+use contracts::shared::marketplace_links::WithMarketplaceLinks;
 use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::domain::a010_ozon_fbs_posting;
 use crate::shared::data::raw_storage;
+use crate::shared::marketplaces::links::{self, OzonScheme};
+use crate::system::access::resolver::can_view_finance;
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::org_context::ActiveOrganization;
+
+async fn finance_access(
+    claims: &contracts::system::auth::TokenClaims,
+) -> Result<bool, axum::http::StatusCode> {
+    can_view_finance(claims).await.map_err(|e| {
+        tracing::error!("Failed to resolve finance access: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
 
 /// Handler для получения списка OZON FBS Posting
-pub async fn list_postings() -> Result<Json<Vec<OzonFbsPosting>>, axum::http::StatusCode> {
-    let items = a010_ozon_fbs_posting::service::list_all()
+pub async fn list_postings(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+) -> Result<Json<Vec<OzonFbsPosting>>, axum::http::StatusCode> {
+    let mut items = a010_ozon_fbs_posting::service::list_all()
         .await
         .map_err(|e| {
             tracing::error!("Failed to list OZON FBS postings: {}", e);
             axum::http::StatusCode::INTERNAL_SERVER_ERROR
         })?;
+    items.retain(|item| active_org.allows(&item.header.organization_id));
 
+    if !finance_access(&claims).await? {
+        items.iter_mut().for_each(FinanceFields::mask_finance);
+    }
     Ok(Json(items))
 }
 
 /// Handler для получения детальной информации о OZON FBS Posting
 pub async fn get_posting_detail(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<OzonFbsPosting>, axum::http::StatusCode> {
+) -> Result<Json<WithMarketplaceLinks<OzonFbsPosting>>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
 
-    let item = a010_ozon_fbs_posting::service::get_by_id(uuid)
+    let mut item = a010_ozon_fbs_posting::service::get_by_id(uuid)
         .await
         .map_err(|e| {
             tracing::error!("Failed to get OZON FBS posting detail: {}", e);
             axum::http::StatusCode::INTERNAL_SERVER_ERROR
         })?
+        .filter(|item| active_org.allows(&item.header.organization_id))
         .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    if !finance_access(&claims).await? {
+        item.mask_finance();
+    }
 
-    Ok(Json(item))
+    Ok(Json(WithMarketplaceLinks {
+        marketplace_links: links::ozon_posting_links(&item.header.document_no, OzonScheme::Fbs),
+        item,
+    }))
 }
 
-/// Handler для получения raw JSON от OZON API по raw_payload_ref
+/// Handler для получения raw JSON от OZON API по raw_payload_ref.
+/// Сырой ответ API содержит суммы, поэтому без доступа к финансам не отдаётся.
 pub async fn get_raw_json(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     axum::extract::Path(ref_id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    if !finance_access(&claims).await? {
+        return Err(axum::http::StatusCode::FORBIDDEN);
+    }
+    if active_org.0.is_some() {
+        // Raw JSON виден, только если ссылающееся на него отправление в текущей организации
+        let visible = a010_ozon_fbs_posting::service::list_all()
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to list OZON FBS postings: {}", e);
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            })?
+            .iter()
+            .any(|item| {
+                item.source_meta.raw_payload_ref == ref_id
+                    && active_org.allows(&item.header.organization_id)
+            });
+        if !visible {
+            return Err(axum::http::StatusCode::NOT_FOUND);
+        }
+    }
     let json_value = raw_storage::get_json_value_by_ref(&ref_id)
         .await
         .map_err(|e| {
@@ -51,11 +105,33 @@ pub async fn get_raw_json(
     Ok(Json(json_value))
 }
 
+/// Отправление есть в текущей организации; отправление другой организации — как
+/// несуществующее.
+async fn ensure_visible(
+    active_org: &ActiveOrganization,
+    uuid: Uuid,
+) -> Result<(), axum::http::StatusCode> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    a010_ozon_fbs_posting::service::get_by_id(uuid)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get posting: {}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .filter(|item| active_org.allows(&item.header.organization_id))
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    Ok(())
+}
+
 /// Handler для проведения документа
 pub async fn post_document(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    ensure_visible(&active_org, uuid).await?;
 
     a010_ozon_fbs_posting::posting::post_document(uuid)
         .await
@@ -69,9 +145,11 @@ pub async fn post_document(
 
 /// Handler для отмены проведения документа
 pub async fn unpost_document(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    ensure_visible(&active_org, uuid).await?;
 
     a010_ozon_fbs_posting::posting::unpost_document(uuid)
         .await
@@ -91,6 +169,7 @@ pub struct PostPeriodRequest {
 
 /// Handler для проведения документов за период
 pub async fn post_period(
+    active_org: ActiveOrganization,
     Query(req): Query<PostPeriodRequest>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let from = NaiveDate::parse_from_str(&req.from, "%Y-%m-%d")
@@ -111,8 +190,12 @@ pub async fn post_period(
     let mut failed_count = 0;
 
     for doc in documents {
+        // Тестовые данные песочницы пакетно не проводятся
+        if doc.is_sandbox {
+            continue;
+        }
         let doc_date = doc.source_meta.fetched_at.date_naive();
-        if doc_date >= from && doc_date <= to {
+        if doc_date >= from && doc_date <= to && active_org.allows(&doc.header.organization_id) {
             match a010_ozon_fbs_posting::posting::post_document(doc.base.id.value()).await {
                 Ok(_) => {
                     posted_count += 1;
@@ -2,45 +2,98 @@ use axum::{extract::Query, Json};
 use chrono::NaiveDate;
 use contracts::domain::a011_ozon_fbo_posting::aggregate::OzonFboPosting;
 use contracts::domain::common::AggregateId;
+use contracts::shared::access::FinanceFields;
+use contracts::shared::marketplace_links::WithMarketplaceLinks;
 use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::domain::a011_ozon_fbo_posting;
+use crate::shared::marketplaces::links::{self, OzonScheme};
+use crate::system::access::resolver::can_view_finance;
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::org_context::ActiveOrganization;
+
+async fn finance_access(
+    claims: &contracts::system::auth::TokenClaims,
+) -> Result<bool, axum::http::StatusCode> {
+    can_view_finance(claims).await.map_err(|e| {
+        tracing::error!("Failed to resolve finance access: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
 
 /// Handler для получения списка OZON FBO Posting
-pub async fn list_postings() -> Result<Json<Vec<OzonFboPosting>>, axum::http::StatusCode> {
-    let items = a011_ozon_fbo_posting::service::list_all()
+pub async fn list_postings(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+) -> Result<Json<Vec<OzonFboPosting>>, axum::http::StatusCode> {
+    let mut items = a011_ozon_fbo_posting::service::list_all()
         .await
         .map_err(|e| {
             tracing::error!("Failed to list OZON FBO postings: {}", e);
             axum::http::StatusCode::INTERNAL_SERVER_ERROR
         })?;
+    items.retain(|item| active_org.allows(&item.header.organization_id));
 
+    if !finance_access(&claims).await? {
+        items.iter_mut().for_each(FinanceFields::mask_finance);
+    }
     Ok(Json(items))
 }
 
 /// Handler для получения детальной информации о OZON FBO Posting
 pub async fn get_posting_detail(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<OzonFboPosting>, axum::http::StatusCode> {
+) -> Result<Json<WithMarketplaceLinks<OzonFboPosting>>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
 
-    let item = a011_ozon_fbo_posting::service::get_by_id(uuid)
+    let mut item = a011_ozon_fbo_posting::service::get_by_id(uuid)
         .await
         .map_err(|e| {
             tracing::error!("Failed to get OZON FBO posting detail: {}", e);
             axum::http::StatusCode::INTERNAL_SERVER_ERROR
         })?
+        .filter(|item| active_org.allows(&item.header.organization_id))
         .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    if !finance_access(&claims).await? {
+        item.mask_finance();
+    }
 
-    Ok(Json(item))
+    Ok(Json(WithMarketplaceLinks {
+        marketplace_links: links::ozon_posting_links(&item.header.document_no, OzonScheme::Fbo),
+        item,
+    }))
+}
+
+/// Отправление есть в текущей организации; отправление другой организации — как
+/// несуществующее.
+async fn ensure_visible(
+    active_org: &ActiveOrganization,
+    uuid: Uuid,
+) -> Result<(), axum::http::StatusCode> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    a011_ozon_fbo_posting::service::get_by_id(uuid)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get posting: {}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .filter(|item| active_org.allows(&item.header.organization_id))
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    Ok(())
 }
 
 /// Handler для проведения документа
 pub async fn post_document(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    ensure_visible(&active_org, uuid).await?;
 
     a011_ozon_fbo_posting::posting::post_document(uuid)
         .await
@@ -54,9 +107,11 @@ pub async fn post_document(
 
 /// Handler для отмены проведения документа
 pub async fn unpost_document(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    ensure_visible(&active_org, uuid).await?;
 
     a011_ozon_fbo_posting::posting::unpost_document(uuid)
         .await
@@ -76,6 +131,7 @@ pub struct PostPeriodRequest {
 
 /// Handler для проведения документов за период
 pub async fn post_period(
+    active_org: ActiveOrganization,
     Query(req): Query<PostPeriodRequest>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let from = NaiveDate::parse_from_str(&req.from, "%Y-%m-%d")
@@ -94,8 +150,12 @@ pub async fn post_period(
     let mut failed_count = 0;
 
     for doc in documents {
+        // Тестовые данные песочницы пакетно не проводятся
+        if doc.is_sandbox {
+            continue;
+        }
         let doc_date = doc.source_meta.fetched_at.date_naive();
-        if doc_date >= from && doc_date <= to {
+        if doc_date >= from && doc_date <= to && active_org.allows(&doc.header.organization_id) {
             match a011_ozon_fbo_posting::posting::post_document(doc.base.id.value()).await {
                 Ok(_) => {
                     posted_count += 1;
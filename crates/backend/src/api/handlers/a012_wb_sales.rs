@@ -1,14 +1,40 @@
-use axum::{extract::Query, Json};
+use anyhow::Context;
+use axum::{
+    extract::Query,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
 use chrono::NaiveDate;
-use contracts::domain::a012_wb_sales::aggregate::WbSales;
+use contracts::domain::a012_wb_sales::aggregate::{
+    WbSales, WbSalesBatchRequest, WbSalesBatchResponse,
+};
+use contracts::domain::a012_wb_sales::compare::{parse_compare_ids, WbSalesCompareResponse};
+use contracts::domain::a012_wb_sales::finance_links::{
+    WbSalesFinanceLinksRequest, WbSalesFinanceLinksResponse, FINANCE_LINK_CANDIDATE_DAYS,
+};
 use contracts::domain::common::AggregateId;
+use contracts::projections::p903_wb_finance_report::dto::WbFinanceReportDto;
+use contracts::shared::access::FinanceFields;
 use contracts::shared::analytics::TurnoverLayer;
+use contracts::shared::delta::{DeltaInfo, DELTA_MAX_ROWS};
+use contracts::shared::marketplace_links::WithMarketplaceLinks;
+use contracts::system::auth::TokenClaims;
+use contracts::system::data_freshness::{DataFreshnessDto, FreshnessDataset};
+use contracts::system::operations::OperationRequest;
+use contracts::system::raw_storage::RawPayloadVersionDto;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::domain::a002_organization;
 use crate::domain::a012_wb_sales;
+use crate::projections::p903_wb_finance_report;
 use crate::shared::data::db::get_connection;
+use crate::shared::delta;
+use crate::shared::error::AppError;
+use crate::shared::marketplaces::links;
+use crate::shared::marketplaces::wildberries::datetime::wb_business_date;
+use crate::system::org_context::ActiveOrganization;
 
 /// Convert empty string to None
 fn non_empty(s: String) -> Option<String> {
@@ -18,7 +44,13 @@ fn non_empty(s: String) -> Option<String> {
         Some(s)
     }
 }
+use crate::api::handlers::p903_wb_finance_report as p903_handlers;
 use crate::shared::data::raw_storage;
+use crate::shared::optimistic_lock::{self, ExpectedVersion};
+use crate::system::access::resolver::can_view_finance;
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::data_freshness;
+use crate::system::operations;
 use sea_orm::{ConnectionTrait, Statement};
 use std::collections::HashMap;
 use std::sync::OnceLock;
@@ -229,6 +261,29 @@ pub struct WbSalesListItemDto {
     pub prod_cost_status: Option<String>,
     pub prod_cost_problem_message: Option<String>,
     pub prod_cost_resolved_total: Option<f64>,
+    /// Значения вычисляемых колонок из `?calc=` (по порядку)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calc_values: Vec<Option<f64>>,
+}
+
+impl FinanceFields for WbSalesListItemDto {
+    const FINANCE_FIELDS: &'static [&'static str] = &[
+        "amount_line",
+        "total_price",
+        "finished_price",
+        "dealer_price_ut",
+        "prod_cost_resolved_total",
+    ];
+
+    /// Вычисляемые колонки строятся из сумм, поэтому стираются вместе с ними.
+    fn mask_finance(&mut self) {
+        self.amount_line = None;
+        self.total_price = None;
+        self.finished_price = None;
+        self.dealer_price_ut = None;
+        self.prod_cost_resolved_total = None;
+        self.calc_values.iter_mut().for_each(|value| *value = None);
+    }
 }
 
 /// Серверные итоги по датасету WB Sales
@@ -240,6 +295,33 @@ pub struct WbSalesTotals {
     pub sum_retail_amount: f64,
 }
 
+impl FinanceFields for WbSalesTotals {
+    const FINANCE_FIELDS: &'static [&'static str] = &["sum_for_pay", "sum_retail_amount"];
+
+    fn mask_finance(&mut self) {
+        self.sum_for_pay = 0.0;
+        self.sum_retail_amount = 0.0;
+    }
+}
+
+async fn finance_access(claims: &TokenClaims) -> Result<bool, AppError> {
+    Ok(can_view_finance(claims)
+        .await
+        .context("Failed to resolve finance access")?)
+}
+
+/// Без доступа к финансам сортировка по сумме раскрыла бы её порядок.
+fn restrict_finance_sort(query: &mut ListSalesQuery, show_finance: bool) {
+    if !show_finance
+        && query
+            .sort_by
+            .as_deref()
+            .is_some_and(|field| WbSalesListItemDto::FINANCE_FIELDS.contains(&field))
+    {
+        query.sort_by = None;
+    }
+}
+
 /// Paginated response for WB Sales list
 #[derive(Debug, Clone, Serialize)]
 pub struct PaginatedWbSalesResponse {
@@ -250,6 +332,13 @@ pub struct PaginatedWbSalesResponse {
     pub total_pages: usize,
     /// Серверные итоги по всему датасету
     pub totals: Option<WbSalesTotals>,
+    /// Водяной знак для следующего `updated_after`
+    pub server_time: String,
+    /// Есть только в режиме дельты: `items` — изменённые строки выборки
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delta: Option<DeltaInfo>,
+    /// Когда данные WB продаж последний раз импортировались (бейдж «данные на …»)
+    pub freshness: Vec<DataFreshnessDto>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -267,44 +356,198 @@ pub struct ListSalesQuery {
     pub search_srid: Option<String>,
     /// Search by supplier_article
     pub search_supplier_article: Option<String>,
+    /// Быстрый фильтр: `status:unposted warehouse:"Коледино" amount>1000`
+    pub q: Option<String>,
+    /// Вычисляемые колонки: `Маржа=amount_line-finished_price;...`
+    pub calc: Option<String>,
+    /// Дельта: только строки, изменённые с этого момента (RFC 3339)
+    pub updated_after: Option<String>,
+}
+
+/// Запрос к `list_sql` по фильтрам списка (общий для списка и выгрузки).
+fn build_list_query(
+    query: &ListSalesQuery,
+    limit: usize,
+    offset: usize,
+    show_finance: bool,
+) -> Result<a012_wb_sales::repository::WbSalesListQuery, AppError> {
+    let quick = crate::shared::quick_filter::build_where(
+        query.q.as_deref().unwrap_or_default(),
+        contracts::domain::a012_wb_sales::quick_filter::QUICK_FILTER_FIELDS,
+        a012_wb_sales::repository::QUICK_FILTER_COLUMNS,
+        show_finance,
+    )
+    .map_err(|e| AppError::bad_request(format!("Некорректный быстрый фильтр: {}", e)))?;
+    let calc = crate::shared::calc_column::from_param(
+        query.calc.as_deref().unwrap_or_default(),
+        contracts::domain::a012_wb_sales::calc_fields::CALC_FIELDS,
+        a012_wb_sales::repository::CALC_COLUMNS,
+    )
+    .map_err(|e| AppError::bad_request(format!("Некорректная вычисляемая колонка: {}", e)))?;
+
+    Ok(a012_wb_sales::repository::WbSalesListQuery {
+        date_from: query.date_from.clone(),
+        date_to: query.date_to.clone(),
+        organization_id: query.organization_id.clone(),
+        search_sale_id: query.search_sale_id.clone(),
+        search_srid: query.search_srid.clone(),
+        search_supplier_article: query.search_supplier_article.clone(),
+        quick_conditions: quick.conditions,
+        search_text: quick.free_text,
+        calc,
+        changed_since: None,
+        sort_by: query
+            .sort_by
+            .clone()
+            .unwrap_or_else(|| "sale_date".to_string()),
+        sort_desc: query.sort_desc.unwrap_or(true),
+        limit,
+        offset,
+    })
+}
+
+/// Справочники для строк списка (организации, товары МП, номенклатура) — из кэша.
+struct ListReferenceMaps {
+    org_map: HashMap<String, String>,
+    mp_map: HashMap<String, (String, Option<String>)>,
+    nom_map: HashMap<String, (String, String)>,
+}
+
+impl ListReferenceMaps {
+    async fn load() -> Self {
+        let (org_map, mp_map, nom_map) = tokio::join!(get_org_map(), get_mp_map(), get_nom_map());
+        Self {
+            org_map,
+            mp_map,
+            nom_map,
+        }
+    }
+
+    fn to_list_item(&self, row: a012_wb_sales::repository::WbSalesListRow) -> WbSalesListItemDto {
+        // Get organization name from cache
+        let organization_name = row.organization_name.clone().or_else(|| {
+            row.organization_id
+                .as_ref()
+                .and_then(|org_id| self.org_map.get(&normalize_id(org_id)).cloned())
+        });
+
+        // Get marketplace article and nomenclature_ref from marketplace_product
+        let (marketplace_article, nomenclature_ref_from_mp) = row
+            .marketplace_product_ref
+            .as_ref()
+            .and_then(|mp_ref| self.mp_map.get(mp_ref).cloned())
+            .unwrap_or((String::new(), None));
+
+        // Get nomenclature data
+        let nom_ref = row
+            .nomenclature_ref
+            .as_ref()
+            .or(nomenclature_ref_from_mp.as_ref());
+        let (nomenclature_code, nomenclature_article) = nom_ref
+            .and_then(|nr| self.nom_map.get(nr).cloned())
+            .unwrap_or((String::new(), String::new()));
+
+        // Get operation date from P903
+        let operation_date = None;
+
+        WbSalesListItemDto {
+            id: row.id,
+            document_no: row.document_no,
+            sale_id: row.sale_id,
+            sale_date: row.sale_date.unwrap_or_default(),
+            supplier_article: row.supplier_article.unwrap_or_default(),
+            name: row.product_name.unwrap_or_default(),
+            qty: row.qty.unwrap_or(0.0),
+            amount_line: row.amount_line,
+            total_price: row.total_price,
+            finished_price: row.finished_price,
+            event_type: row.event_type.unwrap_or_default(),
+            organization_name,
+            marketplace_article: non_empty(marketplace_article),
+            nomenclature_code: non_empty(nomenclature_code),
+            nomenclature_article: non_empty(nomenclature_article),
+            operation_date,
+            dealer_price_ut: row.dealer_price_ut,
+            prod_cost_problem: row.prod_cost_problem,
+            prod_cost_status: row.prod_cost_status,
+            prod_cost_problem_message: row.prod_cost_problem_message,
+            prod_cost_resolved_total: row.prod_cost_resolved_total,
+            calc_values: row.calc_values,
+        }
+    }
 }
 
 /// Handler для получения списка Wildberries Sales с пагинацией
 /// Использует прямой SQL запрос с денормализованными полями (без JSON парсинга)
+///
+/// С `updated_after` / `If-Modified-Since` возвращает только изменения
+/// (`contracts::shared::delta`) или `304 Not Modified`.
 pub async fn list_sales(
-    Query(query): Query<ListSalesQuery>,
-) -> Result<Json<PaginatedWbSalesResponse>, axum::http::StatusCode> {
-    use a012_wb_sales::repository::{list_sql, WbSalesListQuery};
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+    Query(mut query): Query<ListSalesQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    use a012_wb_sales::repository::list_sql;
+
+    query.organization_id = active_org.scope(query.organization_id.take());
+    let show_finance = finance_access(&claims).await?;
+    restrict_finance_sort(&mut query, show_finance);
+
+    let server_time = delta::server_time();
+    let since = delta::parse_since(query.updated_after.as_deref(), &headers)
+        .map_err(AppError::bad_request)?;
 
     let page_size = query.limit.unwrap_or(100);
     let offset = query.offset.unwrap_or(0);
     let page = if page_size > 0 { offset / page_size } else { 0 };
-    let sort_by = query
-        .sort_by
-        .clone()
-        .unwrap_or_else(|| "sale_date".to_string());
-    let sort_desc = query.sort_desc.unwrap_or(true);
 
     // Build query for SQL-based list
-    let list_query = WbSalesListQuery {
-        date_from: query.date_from.clone(),
-        date_to: query.date_to.clone(),
-        organization_id: query.organization_id.clone(),
-        search_sale_id: query.search_sale_id.clone(),
-        search_srid: query.search_srid.clone(),
-        search_supplier_article: query.search_supplier_article.clone(),
-        sort_by: sort_by.clone(),
-        sort_desc,
-        limit: page_size,
-        offset,
+    let list_query = build_list_query(&query, page_size, offset, show_finance)?;
+
+    let (result, delta_info) = match since {
+        Some(since) => {
+            let deleted_ids = a012_wb_sales::repository::deleted_ids_since(&since)
+                .await
+                .context("Failed to list deleted Wildberries sales")?;
+            let mut changed = list_sql(a012_wb_sales::repository::WbSalesListQuery {
+                changed_since: Some(since),
+                limit: DELTA_MAX_ROWS + 1,
+                offset: 0,
+                ..list_query.clone()
+            })
+            .await
+            .context("Failed to list changed Wildberries sales")?;
+            if changed.items.is_empty() && deleted_ids.is_empty() {
+                return Ok(StatusCode::NOT_MODIFIED.into_response());
+            }
+            let truncated = changed.items.len() > DELTA_MAX_ROWS;
+            changed.items.truncate(DELTA_MAX_ROWS);
+            // Число строк всей выборки — для пагинации (LIMIT 0: только COUNT)
+            changed.total = list_sql(a012_wb_sales::repository::WbSalesListQuery {
+                limit: 0,
+                ..list_query.clone()
+            })
+            .await
+            .context("Failed to count Wildberries sales")?
+            .total;
+            (
+                changed,
+                Some(DeltaInfo {
+                    deleted_ids,
+                    truncated,
+                }),
+            )
+        }
+        // Execute SQL query (no caching, direct DB query)
+        None => (
+            list_sql(list_query.clone())
+                .await
+                .context("Failed to list Wildberries sales")?,
+            None,
+        ),
     };
 
-    // Execute SQL query (no caching, direct DB query)
-    let result = list_sql(list_query.clone()).await.map_err(|e| {
-        tracing::error!("Failed to list Wildberries sales: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
-
     let total = result.total;
     let total_pages = if page_size > 0 {
         (total + page_size - 1) / page_size
@@ -313,67 +556,22 @@ pub async fn list_sales(
     };
 
     // Load reference data in parallel (still use caching for these)
-    let (org_map, mp_map, nom_map) = tokio::join!(get_org_map(), get_mp_map(), get_nom_map());
+    let maps = ListReferenceMaps::load().await;
 
     // Build response DTOs with reference data
-    let items: Vec<WbSalesListItemDto> = result
+    let mut items: Vec<WbSalesListItemDto> = result
         .items
         .into_iter()
-        .map(|row| {
-            // Get organization name from cache
-            let organization_name = row.organization_name.clone().or_else(|| {
-                row.organization_id
-                    .as_ref()
-                    .and_then(|org_id| org_map.get(&normalize_id(org_id)).cloned())
-            });
-
-            // Get marketplace article and nomenclature_ref from marketplace_product
-            let (marketplace_article, nomenclature_ref_from_mp) = row
-                .marketplace_product_ref
-                .as_ref()
-                .and_then(|mp_ref| mp_map.get(mp_ref).cloned())
-                .unwrap_or((String::new(), None));
-
-            // Get nomenclature data
-            let nom_ref = row
-                .nomenclature_ref
-                .as_ref()
-                .or(nomenclature_ref_from_mp.as_ref());
-            let (nomenclature_code, nomenclature_article) = nom_ref
-                .and_then(|nr| nom_map.get(nr).cloned())
-                .unwrap_or((String::new(), String::new()));
-
-            // Get operation date from P903
-            let operation_date = None;
-
-            WbSalesListItemDto {
-                id: row.id,
-                document_no: row.document_no,
-                sale_id: row.sale_id,
-                sale_date: row.sale_date.unwrap_or_default(),
-                supplier_article: row.supplier_article.unwrap_or_default(),
-                name: row.product_name.unwrap_or_default(),
-                qty: row.qty.unwrap_or(0.0),
-                amount_line: row.amount_line,
-                total_price: row.total_price,
-                finished_price: row.finished_price,
-                event_type: row.event_type.unwrap_or_default(),
-                organization_name,
-                marketplace_article: non_empty(marketplace_article),
-                nomenclature_code: non_empty(nomenclature_code),
-                nomenclature_article: non_empty(nomenclature_article),
-                operation_date,
-                dealer_price_ut: row.dealer_price_ut,
-                prod_cost_problem: row.prod_cost_problem,
-                prod_cost_status: row.prod_cost_status,
-                prod_cost_problem_message: row.prod_cost_problem_message,
-                prod_cost_resolved_total: row.prod_cost_resolved_total,
-            }
-        })
+        .map(|row| maps.to_list_item(row))
         .collect();
 
     // Рассчитать итоги по всему датасету (с учётом фильтров)
-    let totals = calculate_wb_sales_totals(&list_query).await.ok();
+    let mut totals = calculate_wb_sales_totals(&list_query).await.ok();
+    if !show_finance {
+        items.iter_mut().for_each(FinanceFields::mask_finance);
+        totals.iter_mut().for_each(FinanceFields::mask_finance);
+    }
+    let freshness = data_freshness::for_datasets(&[FreshnessDataset::WbSales]).await;
 
     Ok(Json(PaginatedWbSalesResponse {
         items,
@@ -382,7 +580,108 @@ pub async fn list_sales(
         page_size,
         total_pages,
         totals,
-    }))
+        server_time,
+        delta: delta_info,
+        freshness,
+    })
+    .into_response())
+}
+
+const EXPORT_HEADERS: &[&str] = &[
+    "Document №",
+    "Дата продажи",
+    "Дата операции",
+    "Организация",
+    "Артикул",
+    "Артикул МП",
+    "Артикул 1С",
+    "Код 1С",
+    "Название",
+    "Количество",
+    "К выплате",
+    "Дил. цена УТ",
+    "Полная цена",
+    "Итоговая цена",
+];
+
+fn export_row(item: WbSalesListItemDto) -> Vec<String> {
+    use crate::shared::export::csv::decimal_opt;
+
+    let sale_date = item.sale_date.split('T').next().unwrap_or_default();
+    let sale_date = NaiveDate::parse_from_str(sale_date, "%Y-%m-%d")
+        .map(|d| d.format("%d.%m.%Y").to_string())
+        .unwrap_or(item.sale_date);
+    vec![
+        item.document_no,
+        sale_date,
+        item.operation_date.unwrap_or_default(),
+        item.organization_name.unwrap_or_default(),
+        item.supplier_article,
+        item.marketplace_article.unwrap_or_default(),
+        item.nomenclature_article.unwrap_or_default(),
+        item.nomenclature_code.unwrap_or_default(),
+        item.name,
+        format!("{:.0}", item.qty),
+        decimal_opt(item.amount_line),
+        decimal_opt(item.dealer_price_ut),
+        decimal_opt(item.total_price),
+        decimal_opt(item.finished_price),
+    ]
+    .into_iter()
+    .chain(item.calc_values.into_iter().map(decimal_opt))
+    .collect()
+}
+
+/// GET /api/a012/wb-sales/export?format=csv&... — все строки по фильтрам списка.
+pub async fn export_sales(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+    Query(format): Query<crate::shared::export::csv::ExportFormatQuery>,
+    Query(mut query): Query<ListSalesQuery>,
+) -> Result<axum::response::Response, (axum::http::StatusCode, String)> {
+    use crate::shared::export::csv;
+
+    query.organization_id = active_org.scope(query.organization_id.take());
+    format.ensure_csv()?;
+    let show_finance = finance_access(&claims)
+        .await
+        .map_err(|e| (e.status(), String::new()))?;
+    restrict_finance_sort(&mut query, show_finance);
+    // Проверяем фильтр до начала ответа: ошибка в потоке уже не станет 400.
+    let checked =
+        build_list_query(&query, 0, 0, show_finance).map_err(|e| (e.status(), String::new()))?;
+    let headers: Vec<String> = EXPORT_HEADERS
+        .iter()
+        .map(|h| h.to_string())
+        .chain(checked.calc.names)
+        .collect();
+    let maps = std::sync::Arc::new(ListReferenceMaps::load().await);
+
+    let filename = format!(
+        "wb_sales_{}_{}.csv",
+        query.date_from.as_deref().unwrap_or("all"),
+        query.date_to.as_deref().unwrap_or("all")
+    );
+    let pages = csv::paged(move |offset, limit| {
+        let list_query = build_list_query(&query, limit, offset, show_finance);
+        let maps = maps.clone();
+        async move {
+            let list_query = list_query.map_err(|_| anyhow::anyhow!("invalid quick filter"))?;
+            let result = a012_wb_sales::repository::list_sql(list_query).await?;
+            Ok(result
+                .items
+                .into_iter()
+                .map(|row| {
+                    let mut item = maps.to_list_item(row);
+                    if !show_finance {
+                        item.mask_finance();
+                    }
+                    export_row(item)
+                })
+                .collect())
+        }
+    });
+    Ok(csv::csv_response_with_headers(&filename, headers, pages))
 }
 
 /// Рассчитать итоги по всему датасету WB Sales (с учётом фильтров)
@@ -420,6 +719,15 @@ async fn calculate_wb_sales_totals(
             conditions.push(format!("srid LIKE '%{}%'", srid));
         }
     }
+    if let Some(ref search_text) = query.search_text {
+        conditions.push(format!(
+            "(s.document_no LIKE '%{0}%' OR s.sale_id LIKE '%{0}%' OR \
+             s.supplier_article LIKE '%{0}%' OR s.product_name LIKE '%{0}%')",
+            search_text.replace('\'', "''")
+        ));
+    }
+    // Условия быстрого фильтра ссылаются на алиас `s`
+    conditions.extend(query.quick_conditions.iter().cloned());
 
     let where_clause = conditions.join(" AND ");
 
@@ -430,8 +738,12 @@ async fn calculate_wb_sales_totals(
             COALESCE(SUM(qty), 0) as sum_quantity,
             COALESCE(SUM(finished_price), 0.0) as sum_for_pay,
             COALESCE(SUM(total_price), 0.0) as sum_retail_amount
-        FROM a012_wb_sales 
+        FROM {} s
         WHERE {}",
+        crate::shared::data::projection_archive::source(
+            "a012_wb_sales",
+            query.date_from.as_deref()
+        ),
         where_clause
     );
 
@@ -457,19 +769,25 @@ async fn calculate_wb_sales_totals(
 
 /// Handler для получения детальной информации о Wildberries Sale
 pub async fn get_sale_detail(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<WbSales>, axum::http::StatusCode> {
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+) -> Result<Json<WithMarketplaceLinks<WbSales>>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
 
-    let item = a012_wb_sales::service::get_by_id(uuid)
+    let mut item = a012_wb_sales::service::get_by_id(uuid)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get Wildberries sale detail: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+        .context("Failed to get Wildberries sale detail")?
+        .filter(|item| active_org.allows(&item.header.organization_id))
+        .ok_or_else(|| AppError::not_found("Документ не найден"))?;
+    if !finance_access(&claims).await? {
+        item.mask_finance();
+    }
 
-    Ok(Json(item))
+    Ok(Json(WithMarketplaceLinks {
+        marketplace_links: links::wb_document_links(item.line.nm_id),
+        item,
+    }))
 }
 
 /// Handler для поиска документов по srid (document_no)
@@ -479,60 +797,289 @@ pub struct SearchBySridQuery {
 }
 
 pub async fn search_by_srid(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     Query(query): Query<SearchBySridQuery>,
-) -> Result<Json<Vec<WbSales>>, axum::http::StatusCode> {
-    let items = a012_wb_sales::repository::search_by_document_no(&query.srid)
+) -> Result<Json<Vec<WbSales>>, AppError> {
+    let mut items = a012_wb_sales::repository::search_by_document_no(&query.srid)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to search by srid: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to search by srid")?;
+    items.retain(|item| active_org.allows(&item.header.organization_id));
+    if !finance_access(&claims).await? {
+        items.iter_mut().for_each(FinanceFields::mask_finance);
+    }
 
     Ok(Json(items))
 }
 
-/// Handler для получения raw JSON от WB API по raw_payload_ref
+#[derive(Debug, Deserialize)]
+pub struct CompareQuery {
+    /// id документов через запятую
+    pub ids: String,
+}
+
+/// GET /api/a012/wb-sales/compare?ids=... — ключевые поля 2–5 продаж колонками
+pub async fn compare_sales(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+    Query(query): Query<CompareQuery>,
+) -> Result<Json<WbSalesCompareResponse>, AppError> {
+    let ids = parse_compare_ids(&query.ids).map_err(AppError::bad_request)?;
+    let uuids = ids
+        .iter()
+        .map(|id| Uuid::parse_str(id).map_err(|_| AppError::invalid_id(id)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if active_org.0.is_some() {
+        // Документ другой организации — как несуществующий
+        for uuid in &uuids {
+            a012_wb_sales::service::get_by_id(*uuid)
+                .await
+                .context("Failed to get Wildberries sale")?
+                .filter(|item| active_org.allows(&item.header.organization_id))
+                .ok_or_else(|| AppError::not_found("Документ не найден"))?;
+        }
+    }
+
+    let mut response = a012_wb_sales::compare::compare(&uuids)
+        .await
+        .context("Failed to compare Wildberries sales")?
+        .ok_or_else(|| AppError::not_found("Документ не найден"))?;
+    if !finance_access(&claims).await? {
+        response.mask_finance();
+    }
+
+    Ok(Json(response))
+}
+
+/// Raw JSON виден, только если продажа с его номером документа есть в текущей
+/// организации; иначе — как несуществующий.
+async fn ensure_raw_visible(active_org: &ActiveOrganization, ref_id: &str) -> Result<(), AppError> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    let document_no = raw_storage::document_no_by_ref(ref_id)
+        .await
+        .context("Failed to resolve raw JSON document")?
+        .ok_or_else(|| AppError::not_found("Документ не найден"))?;
+    let sales = a012_wb_sales::repository::search_by_document_no(&document_no)
+        .await
+        .context("Failed to search by srid")?;
+    if sales
+        .iter()
+        .any(|sale| active_org.allows(&sale.header.organization_id))
+    {
+        Ok(())
+    } else {
+        Err(AppError::not_found("Документ не найден"))
+    }
+}
+
+/// Handler для получения raw JSON от WB API по raw_payload_ref.
+/// Сырой ответ API содержит суммы, поэтому без доступа к финансам не отдаётся.
 pub async fn get_raw_json(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     axum::extract::Path(ref_id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+) -> Result<Json<serde_json::Value>, AppError> {
+    if !finance_access(&claims).await? {
+        return Err(AppError::forbidden("Нет доступа к финансовым данным"));
+    }
+    ensure_raw_visible(&active_org, &ref_id).await?;
     let json_value = raw_storage::get_json_value_by_ref(&ref_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get raw JSON: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to get raw JSON")?;
 
     Ok(Json(json_value))
 }
 
+/// Handler для списка сохранённых версий raw JSON документа (история повторных загрузок)
+pub async fn get_raw_versions(
+    active_org: ActiveOrganization,
+    axum::extract::Path(ref_id): axum::extract::Path<String>,
+) -> Result<Json<Vec<RawPayloadVersionDto>>, AppError> {
+    ensure_raw_visible(&active_org, &ref_id).await?;
+    let versions = raw_storage::list_versions_by_ref(&ref_id)
+        .await
+        .context("Failed to list raw JSON versions")?;
+
+    Ok(Json(versions))
+}
+
+/// Сколько кандидатов на ручную привязку отдаём за раз
+const FINANCE_LINK_CANDIDATES_LIMIT: u64 = 200;
+
+/// Продажа, видимая в текущей организации; документ другой организации — как
+/// несуществующий.
+async fn load_sale(active_org: &ActiveOrganization, id: &str) -> Result<WbSales, AppError> {
+    let uuid = Uuid::parse_str(id).map_err(|_| AppError::invalid_id(id))?;
+    a012_wb_sales::service::get_by_id(uuid)
+        .await
+        .with_context(|| format!("Failed to load a012 {}", id))?
+        .filter(|sale| active_org.allows(&sale.header.organization_id))
+        .ok_or_else(|| AppError::not_found("Документ не найден"))
+}
+
+/// Проверка организации для эндпоинтов по id документа, которым сам документ
+/// не нужен. Без выбранной организации документ не загружается.
+async fn ensure_visible(active_org: &ActiveOrganization, id: &str) -> Result<(), AppError> {
+    if active_org.0.is_some() {
+        load_sale(active_org, id).await?;
+    }
+    Ok(())
+}
+
+async fn finance_links_response(sale_id: &str) -> Result<WbSalesFinanceLinksResponse, AppError> {
+    let rrd_ids = a012_wb_sales::finance_links::list_rrd_ids(sale_id)
+        .await
+        .context("Failed to list a012 finance links")?;
+    let models = p903_wb_finance_report::repository::list_by_rrd_ids(&rrd_ids)
+        .await
+        .context("Failed to load linked finance report rows")?;
+    let missing_rrd_ids = rrd_ids
+        .iter()
+        .copied()
+        .filter(|rrd_id| !models.iter().any(|m| m.rrd_id == *rrd_id))
+        .collect();
+    Ok(WbSalesFinanceLinksResponse {
+        rows: models
+            .into_iter()
+            .map(|m| p903_handlers::model_to_dto(m, 0))
+            .collect(),
+        missing_rrd_ids,
+    })
+}
+
+/// GET /api/a012/wb-sales/:id/finance-links — строки p903, привязанные вручную
+pub async fn list_finance_links(
+    active_org: ActiveOrganization,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> Result<Json<WbSalesFinanceLinksResponse>, AppError> {
+    load_sale(&active_org, &id).await?;
+    Ok(Json(finance_links_response(&id).await?))
+}
+
+/// POST /api/a012/wb-sales/:id/finance-links — привязать строки p903 по rrd_id
+pub async fn attach_finance_links(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+    axum::extract::Path(id): axum::extract::Path<String>,
+    Json(request): Json<WbSalesFinanceLinksRequest>,
+) -> Result<Json<WbSalesFinanceLinksResponse>, AppError> {
+    load_sale(&active_org, &id).await?;
+    let rrd_ids = a012_wb_sales::finance_links::normalize_rrd_ids(request.rrd_ids)
+        .map_err(|e| AppError::bad_request(e.to_string()))?;
+    let existing = p903_wb_finance_report::repository::list_by_rrd_ids(&rrd_ids)
+        .await
+        .context("Failed to check finance report rows")?;
+    let unknown: Vec<String> = rrd_ids
+        .iter()
+        .filter(|rrd_id| !existing.iter().any(|m| m.rrd_id == **rrd_id))
+        .map(ToString::to_string)
+        .collect();
+    if !unknown.is_empty() {
+        return Err(AppError::bad_request(format!(
+            "Строки финансового отчёта не найдены: {}",
+            unknown.join(", ")
+        )));
+    }
+    a012_wb_sales::finance_links::attach(&id, &rrd_ids, Some(&claims.sub))
+        .await
+        .context("Failed to attach finance report rows")?;
+    Ok(Json(finance_links_response(&id).await?))
+}
+
+/// DELETE /api/a012/wb-sales/:id/finance-links — отвязать строки p903
+pub async fn detach_finance_links(
+    active_org: ActiveOrganization,
+    axum::extract::Path(id): axum::extract::Path<String>,
+    Json(request): Json<WbSalesFinanceLinksRequest>,
+) -> Result<Json<WbSalesFinanceLinksResponse>, AppError> {
+    load_sale(&active_org, &id).await?;
+    let rrd_ids = a012_wb_sales::finance_links::normalize_rrd_ids(request.rrd_ids)
+        .map_err(|e| AppError::bad_request(e.to_string()))?;
+    a012_wb_sales::finance_links::detach(&id, &rrd_ids)
+        .await
+        .context("Failed to detach finance report rows")?;
+    Ok(Json(finance_links_response(&id).await?))
+}
+
+/// GET /api/a012/wb-sales/:id/finance-links/candidates — строки p903 того же nm_id
+/// около даты продажи, которые не находятся по SRID и ещё не привязаны
+pub async fn finance_link_candidates(
+    active_org: ActiveOrganization,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> Result<Json<Vec<WbFinanceReportDto>>, AppError> {
+    let sale = load_sale(&active_org, &id).await?;
+    let sale_date = wb_business_date(&sale.state.sale_dt);
+    let window = chrono::Duration::days(FINANCE_LINK_CANDIDATE_DAYS);
+    let date_from = (sale_date - window).format("%Y-%m-%d").to_string();
+    let date_to = (sale_date + window).format("%Y-%m-%d").to_string();
+
+    let linked = a012_wb_sales::finance_links::list_rrd_ids(&id)
+        .await
+        .context("Failed to list a012 finance links")?;
+    let candidates = p903_wb_finance_report::repository::search_link_candidates(
+        sale.line.nm_id,
+        &date_from,
+        &date_to,
+        &sale.header.document_no,
+        FINANCE_LINK_CANDIDATES_LIMIT,
+    )
+    .await
+    .context("Failed to search finance report link candidates")?;
+
+    Ok(Json(
+        candidates
+            .into_iter()
+            .filter(|m| !linked.contains(&m.rrd_id))
+            .map(|m| p903_handlers::model_to_dto(m, 0))
+            .collect(),
+    ))
+}
+
 /// Handler для проведения документа
 pub async fn post_document(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    Query(expected): Query<ExpectedVersion>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
+    ensure_visible(&active_org, &id).await?;
 
-    a012_wb_sales::posting::post_document(uuid)
+    a012_wb_sales::posting::post_document_at_version(uuid, expected.version)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to post document: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .map_err(|e| optimistic_lock::command_error(e, "Failed to post document"))?;
 
     Ok(Json(serde_json::json!({"success": true})))
 }
 
+/// Handler предпросмотра проведения: строки p900/p904 до и после, без записи в БД
+pub async fn post_preview(
+    active_org: ActiveOrganization,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> Result<Json<a012_wb_sales::posting::PostingPreview>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
+    ensure_visible(&active_org, &id).await?;
+
+    let preview = a012_wb_sales::posting::preview_posting(uuid)
+        .await
+        .with_context(|| format!("Failed to build posting preview for {}", id))?;
+
+    Ok(Json(preview))
+}
+
 /// Handler для отмены проведения документа
 pub async fn unpost_document(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    Query(expected): Query<ExpectedVersion>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
+    ensure_visible(&active_org, &id).await?;
 
-    a012_wb_sales::posting::unpost_document(uuid)
+    a012_wb_sales::posting::unpost_document_at_version(uuid, expected.version)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to unpost document: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .map_err(|e| optimistic_lock::command_error(e, "Failed to unpost document"))?;
 
     Ok(Json(serde_json::json!({"success": true})))
 }
@@ -543,29 +1090,31 @@ pub struct PostPeriodRequest {
     pub to: String,
 }
 
-#[derive(Deserialize)]
-pub struct BatchOperationRequest {
-    pub ids: Vec<String>,
-}
-
 /// Handler для проведения документов за период
 pub async fn post_period(
+    active_org: ActiveOrganization,
     Query(req): Query<PostPeriodRequest>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+) -> Result<Json<serde_json::Value>, AppError> {
     let from = NaiveDate::parse_from_str(&req.from, "%Y-%m-%d")
-        .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+        .map_err(|_| AppError::bad_request(format!("Некорректная дата: {}", req.from)))?;
     let to = NaiveDate::parse_from_str(&req.to, "%Y-%m-%d")
-        .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+        .map_err(|_| AppError::bad_request(format!("Некорректная дата: {}", req.to)))?;
 
-    let documents = a012_wb_sales::service::list_all().await.map_err(|e| {
-        tracing::error!("Failed to list documents: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let documents = a012_wb_sales::service::list_all()
+        .await
+        .context("Failed to list documents")?;
 
     let mut posted_count = 0;
     let mut failed_count = 0;
 
     for doc in documents {
+        // Тестовые данные песочницы пакетно не проводятся
+        if doc.is_sandbox {
+            continue;
+        }
+        if !active_org.allows(&doc.header.organization_id) {
+            continue;
+        }
         let doc_date = doc.source_meta.fetched_at.date_naive();
         if doc_date >= from && doc_date <= to {
             match a012_wb_sales::posting::post_document(doc.base.id.value()).await {
@@ -587,90 +1136,132 @@ pub async fn post_period(
     })))
 }
 
-/// Handler для пакетного проведения документов (до 100 документов за раз)
-pub async fn batch_post_documents(
-    Json(req): Json<BatchOperationRequest>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let total = req.ids.len();
-    let mut succeeded = 0;
-    let mut failed = 0;
-
-    for id_str in req.ids {
-        let uuid = match Uuid::parse_str(&id_str) {
-            Ok(uuid) => uuid,
-            Err(_) => {
-                failed += 1;
-                continue;
-            }
-        };
+/// id пакета, которых нет среди `documents` (id, организация) текущей организации:
+/// не найденные и документы других организаций.
+fn hidden_ids<'a>(
+    active_org: &ActiveOrganization,
+    ids: &'a [String],
+    documents: &[(String, String)],
+) -> Vec<&'a str> {
+    ids.iter()
+        .map(String::as_str)
+        .filter(|id| {
+            !documents.iter().any(|(doc_id, organization_id)| {
+                doc_id.as_str() == *id && active_org.allows(organization_id)
+            })
+        })
+        .collect()
+}
 
-        match a012_wb_sales::posting::post_document(uuid).await {
-            Ok(_) => succeeded += 1,
-            Err(_) => failed += 1,
+/// Документы пакета: явный список или продажи периода (для отмены — только проведённые).
+/// При выбранной организации документ другой организации отклоняет пакет как
+/// несуществующий, а период ограничивается организацией.
+async fn resolve_batch_ids(
+    active_org: &ActiveOrganization,
+    req: &WbSalesBatchRequest,
+    only_posted: bool,
+) -> Result<Vec<String>, AppError> {
+    if !req.ids.is_empty() {
+        if active_org.0.is_some() {
+            let documents: Vec<(String, String)> = a012_wb_sales::repository::list_by_ids(&req.ids)
+                .await
+                .context("Failed to load WB sales for batch")?
+                .into_iter()
+                .map(|doc| (doc.base.id.as_string(), doc.header.organization_id))
+                .collect();
+            let hidden = hidden_ids(active_org, &req.ids, &documents);
+            if !hidden.is_empty() {
+                return Err(AppError::not_found(format!(
+                    "Документы не найдены: {}",
+                    hidden.join(", ")
+                )));
+            }
         }
+        return Ok(req.ids.clone());
     }
-
-    tracing::info!(
-        "Batch posted {} documents (succeeded: {}, failed: {})",
-        total,
-        succeeded,
-        failed
-    );
-
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "succeeded": succeeded,
-        "failed": failed,
-        "total": total
-    })))
+    let (Some(date_from), Some(date_to)) = (req.date_from.as_deref(), req.date_to.as_deref())
+    else {
+        return Err(AppError::bad_request("Укажите документы или период"));
+    };
+    for date in [date_from, date_to] {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| AppError::bad_request(format!("Некорректная дата: {}", date)))?;
+    }
+    let ids = a012_wb_sales::repository::list_ids_by_sale_date_range(
+        date_from,
+        date_to,
+        only_posted,
+        active_org.0.as_deref(),
+    )
+    .await
+    .context("Failed to list WB sales for batch")?;
+    Ok(ids)
 }
 
-/// Handler для пакетной отмены проведения документов (до 100 документов за раз)
-pub async fn batch_unpost_documents(
-    Json(req): Json<BatchOperationRequest>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let total = req.ids.len();
-    let mut succeeded = 0;
-    let mut failed = 0;
-
-    for id_str in req.ids {
-        let uuid = match Uuid::parse_str(&id_str) {
-            Ok(uuid) => uuid,
-            Err(_) => {
-                failed += 1;
-                continue;
-            }
-        };
-
-        match a012_wb_sales::posting::unpost_document(uuid).await {
-            Ok(_) => succeeded += 1,
-            Err(_) => failed += 1,
-        }
+/// Ставит пакет в очередь пакетных операций — каждый документ проводится отдельно,
+/// ошибка одного не откатывает остальные; итог по документам — в операции.
+async fn start_batch(
+    claims: &TokenClaims,
+    request: OperationRequest,
+) -> Result<Json<WbSalesBatchResponse>, AppError> {
+    operations::service::validate(&request).map_err(|e| AppError::bad_request(e.to_string()))?;
+    let allowed = operations::service::is_allowed(&request, claims)
+        .await
+        .context("Failed to check WB sales batch access")?;
+    if !allowed {
+        return Err(AppError::forbidden(
+            "Недостаточно прав для проведения документов",
+        ));
     }
+    let document_count = request.item_count();
+    let operation = operations::service::start(request, &claims.sub)
+        .await
+        .context("Failed to start WB sales batch")?;
+    Ok(Json(WbSalesBatchResponse {
+        operation_id: operation.id,
+        document_count,
+    }))
+}
 
-    tracing::info!(
-        "Batch unposted {} documents (succeeded: {}, failed: {})",
-        total,
-        succeeded,
-        failed
-    );
+/// POST /api/a012/wb-sales/batch-post — пакетное проведение по списку или периоду
+pub async fn batch_post(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+    Json(req): Json<WbSalesBatchRequest>,
+) -> Result<Json<WbSalesBatchResponse>, AppError> {
+    let ids = resolve_batch_ids(&active_org, &req, false).await?;
+    start_batch(
+        &claims,
+        OperationRequest::PostDocuments {
+            entity_type: "a012_wb_sales".to_string(),
+            ids,
+        },
+    )
+    .await
+}
 
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "succeeded": succeeded,
-        "failed": failed,
-        "total": total
-    })))
+/// POST /api/a012/wb-sales/batch-unpost — пакетная отмена проведения по списку или периоду
+pub async fn batch_unpost(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+    Json(req): Json<WbSalesBatchRequest>,
+) -> Result<Json<WbSalesBatchResponse>, AppError> {
+    let ids = resolve_batch_ids(&active_org, &req, true).await?;
+    start_batch(
+        &claims,
+        OperationRequest::UnpostDocuments {
+            entity_type: "a012_wb_sales".to_string(),
+            ids,
+        },
+    )
+    .await
 }
 
 /// Handler для миграции старых документов: денормализация всех полей из JSON
-pub async fn migrate_fill_sale_id() -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+pub async fn migrate_fill_sale_id() -> Result<Json<serde_json::Value>, AppError> {
     let updated = crate::shared::data::db::migrate_wb_sales_denormalize()
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to migrate WB Sales: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to migrate WB Sales")?;
 
     Ok(Json(serde_json::json!({
         "success": true,
@@ -681,22 +1272,18 @@ pub async fn migrate_fill_sale_id() -> Result<Json<serde_json::Value>, axum::htt
 
 /// Handler для получения проекций по registrator_ref
 pub async fn get_projections(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+) -> Result<Json<serde_json::Value>, AppError> {
+    ensure_visible(&active_org, &id).await?;
     // Load p900 and p904 projections for a012_wb_sales
     let p900_items = crate::projections::p900_mp_sales_register::service::get_by_registrator(&id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get p900 projections: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to get p900 projections")?;
 
     let p904_items = crate::projections::p904_sales_data::repository::get_by_registrator(&id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get p904 projections: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to get p904 projections")?;
 
     let p913_items =
         crate::projections::p913_wb_advert_order_attr::repository::list_by_registrator(
@@ -704,10 +1291,7 @@ pub async fn get_projections(
             &id,
         )
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get p913 projections for a012 {}: {}", id, e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .with_context(|| format!("Failed to get p913 projections for a012 {}", id))?;
     let p913_expense: Vec<_> = p913_items
         .into_iter()
         .filter(|row| row.turnover_code == "advert_clicks_order_expense")
@@ -720,10 +1304,7 @@ pub async fn get_projections(
             &id,
         )
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get p916 projections for a012 {}: {}", id, e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .with_context(|| format!("Failed to get p916 projections for a012 {}", id))?;
 
     // Объединяем результаты
     let result = serde_json::json!({
@@ -738,17 +1319,17 @@ pub async fn get_projections(
 
 /// Handler для получения записей журнала операций по registrator_ref
 pub async fn get_general_ledger_entries(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+) -> Result<Json<serde_json::Value>, AppError> {
     use crate::general_ledger::turnover_registry::get_turnover_class;
     use contracts::general_ledger::GeneralLedgerEntryDto;
 
+    ensure_visible(&active_org, &id).await?;
+
     let rows = crate::general_ledger::repository::list_by_registrator("a012_wb_sales", &id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get journal entries: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to get journal entries")?;
 
     // Обогащаем каждую запись комментарием из реестра оборотов.
     let entries: Vec<GeneralLedgerEntryDto> = rows
@@ -851,19 +1432,18 @@ pub struct AdvertAttributionResponse {
 /// `order_key=srid(document_no)`, Р В РЎвЂўР В Р’В±Р В РЎвЂўР В РЎвЂ“Р В Р’В°Р РЋРІР‚В°Р РЋРІР‚ВР В Р вЂ¦Р В Р вЂ¦Р РЋРІР‚в„–Р В Р’Вµ Р В РЎвЂР В Р вЂ¦Р РЋРІР‚С›Р В РЎвЂўР РЋР вЂљР В РЎВР В Р’В°Р РЋРІР‚В Р В РЎвЂР В Р’ВµР В РІвЂћвЂ“ Р В РЎвЂўР В Р’В± Р В РЎвЂР РЋР С“Р РЋРІР‚В¦Р В РЎвЂўР В РўвЂР В Р вЂ¦Р В РЎвЂўР В РЎВ a026,
 /// Р В РЎвЂ”Р В Р’В»Р РЋР вЂ№Р РЋР С“ Р РЋР С“Р РЋР вЂљР В Р’В°Р В Р вЂ Р В Р вЂ¦Р В Р’ВµР В Р вЂ¦Р В РЎвЂР В Р’Вµ Р С›Р в‚¬(amount) Р РЋР С“ Р В РЎвЂ”Р РЋР вЂљР В РЎвЂўР В Р вЂ Р В РЎвЂўР В РўвЂР В РЎвЂќР В РЎвЂўР В РІвЂћвЂ“ `advert_clicks_order_expense` Р В Р вЂ  Р В Р’В¶Р РЋРЎвЂњР РЋР вЂљР В Р вЂ¦Р В Р’В°Р В Р’В»Р В Р’Вµ.
 pub async fn get_advert_attribution(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<AdvertAttributionResponse>, axum::http::StatusCode> {
+) -> Result<Json<AdvertAttributionResponse>, AppError> {
     use std::collections::{HashMap, HashSet};
 
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
 
     let sale = a012_wb_sales::service::get_by_id(uuid)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to load a012 {} for attribution: {}", id, e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+        .with_context(|| format!("Failed to load a012 {} for attribution", id))?
+        .filter(|sale| active_org.allows(&sale.header.organization_id))
+        .ok_or_else(|| AppError::not_found("Документ не найден"))?;
 
     let srid = sale.header.document_no.clone();
 
@@ -873,10 +1453,7 @@ pub async fn get_advert_attribution(
             "advert_clicks_order_accrual",
         )
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to list p913 reserves for srid {}: {}", srid, e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .with_context(|| format!("Failed to list p913 reserves for srid {}", srid))?;
 
     let nom_map = get_nom_map().await;
 
@@ -1000,16 +1577,96 @@ pub async fn get_advert_attribution(
 
 /// Handler Р В РўвЂР В Р’В»Р РЋР РЏ Р В РЎвЂўР В Р’В±Р В Р вЂ¦Р В РЎвЂўР В Р вЂ Р В Р’В»Р В Р’ВµР В Р вЂ¦Р В РЎвЂР РЋР РЏ dealer_price_ut
 pub async fn refresh_dealer_price(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+) -> Result<Json<serde_json::Value>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
+    ensure_visible(&active_org, &id).await?;
 
     a012_wb_sales::service::refresh_dealer_price(uuid)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to refresh dealer price: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to refresh dealer price")?;
 
     Ok(Json(serde_json::json!({"success": true})))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWN: &str = "5f0c8a8e-1111-4a4a-9b9b-000000000001";
+    const OTHER: &str = "5f0c8a8e-2222-4a4a-9b9b-000000000002";
+
+    #[test]
+    fn batch_rejects_documents_of_other_organizations() {
+        let ids = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let documents = vec![
+            ("a".to_string(), OWN.to_string()),
+            ("b".to_string(), OTHER.to_string()),
+        ];
+
+        let own = ActiveOrganization(Some(OWN.to_string()));
+        assert_eq!(hidden_ids(&own, &ids, &documents), vec!["b", "c"]);
+
+        let all = ActiveOrganization::default();
+        assert_eq!(hidden_ids(&all, &ids, &documents), vec!["c"]);
+    }
+
+    #[test]
+    fn list_item_hides_amounts_without_finance_access() {
+        let mut item = WbSalesListItemDto {
+            id: "id".to_string(),
+            document_no: "srid".to_string(),
+            sale_id: Some("S1".to_string()),
+            sale_date: "2026-01-01".to_string(),
+            supplier_article: "ART".to_string(),
+            name: "Товар".to_string(),
+            qty: 2.0,
+            amount_line: Some(100.0),
+            total_price: Some(150.0),
+            finished_price: Some(120.0),
+            event_type: "sale".to_string(),
+            organization_name: None,
+            marketplace_article: None,
+            nomenclature_code: None,
+            nomenclature_article: None,
+            operation_date: None,
+            dealer_price_ut: Some(80.0),
+            prod_cost_problem: false,
+            prod_cost_status: None,
+            prod_cost_problem_message: None,
+            prod_cost_resolved_total: Some(70.0),
+            calc_values: vec![Some(20.0)],
+        };
+        item.mask_finance();
+
+        assert_eq!(item.amount_line, None);
+        assert_eq!(item.total_price, None);
+        assert_eq!(item.finished_price, None);
+        assert_eq!(item.dealer_price_ut, None);
+        assert_eq!(item.prod_cost_resolved_total, None);
+        assert_eq!(item.calc_values, vec![None]);
+        assert_eq!(item.qty, 2.0);
+        assert_eq!(item.supplier_article, "ART");
+
+        let mut totals = WbSalesTotals {
+            total_records: 1,
+            sum_quantity: 2,
+            sum_for_pay: 100.0,
+            sum_retail_amount: 150.0,
+        };
+        totals.mask_finance();
+        assert_eq!((totals.sum_for_pay, totals.sum_retail_amount), (0.0, 0.0));
+        assert_eq!(totals.sum_quantity, 2);
+    }
+
+    #[test]
+    fn finance_sort_falls_back_to_default_without_access() {
+        let mut query: ListSalesQuery =
+            serde_json::from_value(serde_json::json!({ "sort_by": "finished_price" })).unwrap();
+        restrict_finance_sort(&mut query, true);
+        assert_eq!(query.sort_by.as_deref(), Some("finished_price"));
+        restrict_finance_sort(&mut query, false);
+        assert_eq!(query.sort_by, None);
+    }
+}
@@ -2,19 +2,39 @@ use axum::{extract::Query, Json};
 use chrono::NaiveDate;
 use contracts::domain::a013_ym_order::aggregate::{YmOrder, YmOrderListDto};
 use contracts::domain::common::AggregateId;
+use contracts::shared::access::FinanceFields;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::domain::a013_ym_order;
 use crate::shared::data::raw_storage;
+use crate::system::access::resolver::can_view_finance;
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::org_context::ActiveOrganization;
+
+async fn finance_access(
+    claims: &contracts::system::auth::TokenClaims,
+) -> Result<bool, axum::http::StatusCode> {
+    can_view_finance(claims).await.map_err(|e| {
+        tracing::error!("Failed to resolve finance access: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
 
 /// Handler для получения списка Yandex Market Orders (full - с JSON parsing)
-pub async fn list_orders() -> Result<Json<Vec<YmOrder>>, axum::http::StatusCode> {
-    let items = a013_ym_order::service::list_all().await.map_err(|e| {
+pub async fn list_orders(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+) -> Result<Json<Vec<YmOrder>>, axum::http::StatusCode> {
+    let mut items = a013_ym_order::service::list_all().await.map_err(|e| {
         tracing::error!("Failed to list Yandex Market orders: {}", e);
         axum::http::StatusCode::INTERNAL_SERVER_ERROR
     })?;
+    items.retain(|item| active_org.allows(&item.header.organization_id));
 
+    if !finance_access(&claims).await? {
+        items.iter_mut().for_each(FinanceFields::mask_finance);
+    }
     Ok(Json(items))
 }
 
@@ -60,8 +80,11 @@ pub struct PaginatedYmOrderResponse {
 
 /// Handler для получения списка с серверной пагинацией
 pub async fn list_orders_fast(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     Query(params): Query<ListQueryParams>,
 ) -> Result<Json<PaginatedYmOrderResponse>, axum::http::StatusCode> {
+    let show_finance = finance_access(&claims).await?;
     let page_size = params.limit;
     let offset = params.offset;
     let page = if page_size > 0 { offset / page_size } else { 0 };
@@ -69,10 +92,17 @@ pub async fn list_orders_fast(
     let query = a013_ym_order::repository::YmOrderListQuery {
         date_from: params.date_from,
         date_to: params.date_to,
-        organization_id: params.organization_id,
+        organization_id: active_org.scope(params.organization_id),
         search_document_no: params.search_document_no,
         status_norm: params.status_norm,
-        sort_by: params.sort_by,
+        // Без доступа к финансам сортировка по сумме раскрыла бы её порядок.
+        sort_by: if show_finance
+            || !YmOrderListDto::FINANCE_FIELDS.contains(&params.sort_by.as_str())
+        {
+            params.sort_by
+        } else {
+            default_sort_by()
+        },
         sort_desc: params.sort_desc,
         limit: page_size,
         offset,
@@ -105,7 +135,7 @@ pub async fn list_orders_fast(
         .map(|org| (org.base.id.as_string(), org.base.description.clone()))
         .collect();
 
-    let items: Vec<YmOrderListDto> = result
+    let mut items: Vec<YmOrderListDto> = result
         .items
         .into_iter()
         .map(|row| {
@@ -138,6 +168,9 @@ pub async fn list_orders_fast(
             }
         })
         .collect();
+    if !show_finance {
+        items.iter_mut().for_each(FinanceFields::mask_finance);
+    }
 
     Ok(Json(PaginatedYmOrderResponse {
         items,
@@ -150,25 +183,82 @@ pub async fn list_orders_fast(
 
 /// Handler для получения детальной информации о Yandex Market Order
 pub async fn get_order_detail(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<YmOrder>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
 
-    let item = a013_ym_order::service::get_by_id(uuid)
+    let mut item = a013_ym_order::service::get_by_id(uuid)
         .await
         .map_err(|e| {
             tracing::error!("Failed to get Yandex Market order detail: {}", e);
             axum::http::StatusCode::INTERNAL_SERVER_ERROR
         })?
+        .filter(|item| active_org.allows(&item.header.organization_id))
         .ok_or(axum::http::StatusCode::NOT_FOUND)?;
 
+    if !finance_access(&claims).await? {
+        item.mask_finance();
+    }
     Ok(Json(item))
 }
 
+/// Заказ есть в текущей организации; заказ другой организации — 404.
+/// Без выбранной организации документ не загружается.
+async fn ensure_visible(
+    active_org: &ActiveOrganization,
+    uuid: Uuid,
+) -> Result<(), axum::http::StatusCode> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    a013_ym_order::service::get_by_id(uuid)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get Yandex Market order: {}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .filter(|item| active_org.allows(&item.header.organization_id))
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    Ok(())
+}
+
+/// Raw JSON виден, только если заказ с его номером документа есть в текущей
+/// организации.
+async fn ensure_raw_visible(
+    active_org: &ActiveOrganization,
+    ref_id: &str,
+) -> Result<(), axum::http::StatusCode> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    let internal = |e: anyhow::Error| {
+        tracing::error!("Failed to resolve raw JSON document: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    };
+    let document_no = raw_storage::document_no_by_ref(ref_id)
+        .await
+        .map_err(internal)?
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    a013_ym_order::repository::get_by_document_no(&document_no)
+        .await
+        .map_err(internal)?
+        .filter(|item| active_org.allows(&item.header.organization_id))
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    Ok(())
+}
+
 /// Handler для получения raw JSON от Yandex Market API по raw_payload_ref
 pub async fn get_raw_json(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     axum::extract::Path(ref_id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    if !finance_access(&claims).await? {
+        return Err(axum::http::StatusCode::FORBIDDEN);
+    }
+    ensure_raw_visible(&active_org, &ref_id).await?;
     let json_value = raw_storage::get_json_value_by_ref(&ref_id)
         .await
         .map_err(|e| {
@@ -181,9 +271,11 @@ pub async fn get_raw_json(
 
 /// Handler для проведения документа
 pub async fn post_document(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    ensure_visible(&active_org, uuid).await?;
 
     a013_ym_order::posting::post_document(uuid)
         .await
@@ -197,9 +289,11 @@ pub async fn post_document(
 
 /// Handler для отмены проведения документа
 pub async fn unpost_document(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    ensure_visible(&active_org, uuid).await?;
 
     a013_ym_order::posting::unpost_document(uuid)
         .await
@@ -217,13 +311,9 @@ pub struct PostPeriodRequest {
     pub to: String,
 }
 
-#[derive(Deserialize)]
-pub struct BatchOperationRequest {
-    pub ids: Vec<String>,
-}
-
 /// Handler для проведения документов за период
 pub async fn post_period(
+    active_org: ActiveOrganization,
     Query(req): Query<PostPeriodRequest>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let from = NaiveDate::parse_from_str(&req.from, "%Y-%m-%d")
@@ -240,6 +330,13 @@ pub async fn post_period(
     let mut failed_count = 0;
 
     for doc in documents {
+        // Тестовые данные песочницы пакетно не проводятся
+        if doc.is_sandbox {
+            continue;
+        }
+        if !active_org.allows(&doc.header.organization_id) {
+            continue;
+        }
         let doc_date = doc.source_meta.fetched_at.date_naive();
         if doc_date >= from && doc_date <= to {
             match a013_ym_order::posting::post_document(doc.base.id.value()).await {
@@ -262,86 +359,20 @@ pub async fn post_period(
     })))
 }
 
-/// Handler для пакетного проведения документов (до 100 документов за раз)
-pub async fn batch_post_documents(
-    Json(req): Json<BatchOperationRequest>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let total = req.ids.len();
-    let mut succeeded = 0;
-    let mut failed = 0;
-
-    for id_str in req.ids {
-        let uuid = match Uuid::parse_str(&id_str) {
-            Ok(uuid) => uuid,
-            Err(_) => {
-                failed += 1;
-                continue;
-            }
-        };
-
-        match a013_ym_order::posting::post_document(uuid).await {
-            Ok(_) => succeeded += 1,
-            Err(_) => failed += 1,
-        }
-    }
-
-    tracing::info!(
-        "Batch posted {} YM Order documents (succeeded: {}, failed: {})",
-        total,
-        succeeded,
-        failed
-    );
-
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "succeeded": succeeded,
-        "failed": failed,
-        "total": total
-    })))
-}
-
-/// Handler для пакетной отмены проведения документов (до 100 документов за раз)
-pub async fn batch_unpost_documents(
-    Json(req): Json<BatchOperationRequest>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let total = req.ids.len();
-    let mut succeeded = 0;
-    let mut failed = 0;
-
-    for id_str in req.ids {
-        let uuid = match Uuid::parse_str(&id_str) {
-            Ok(uuid) => uuid,
-            Err(_) => {
-                failed += 1;
-                continue;
-            }
-        };
-
-        match a013_ym_order::posting::unpost_document(uuid).await {
-            Ok(_) => succeeded += 1,
-            Err(_) => failed += 1,
-        }
-    }
-
-    tracing::info!(
-        "Batch unposted {} YM Order documents (succeeded: {}, failed: {})",
-        total,
-        succeeded,
-        failed
-    );
-
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "succeeded": succeeded,
-        "failed": failed,
-        "total": total
-    })))
-}
-
 /// Handler для получения проекций по registrator_ref
 pub async fn get_projections(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    // Движения регистров — это суммы; без доступа к финансам не отдаются.
+    if !finance_access(&claims).await? {
+        return Err(axum::http::StatusCode::FORBIDDEN);
+    }
+    if active_org.0.is_some() {
+        let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+        ensure_visible(&active_org, uuid).await?;
+    }
     // Получаем данные из проекций p900 и p904
     let p900_items = crate::projections::p900_mp_sales_register::service::get_by_registrator(&id)
         .await
@@ -1,8 +1,13 @@
+use anyhow::Context;
+use axum::extract::Query;
 use axum::Json;
 use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::domain::a014_ozon_transactions;
+use crate::shared::error::AppError;
+use crate::shared::optimistic_lock::{self, ExpectedVersion};
+use crate::system::org_context::ActiveOrganization;
 
 #[derive(Debug, Deserialize)]
 pub struct ListFilters {
@@ -15,56 +20,151 @@ pub struct ListFilters {
 
 /// Handler для получения списка всех транзакций с фильтрами
 pub async fn list_all(
+    active_org: ActiveOrganization,
     axum::extract::Query(filters): axum::extract::Query<ListFilters>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+) -> Result<Json<serde_json::Value>, AppError> {
     let transactions = a014_ozon_transactions::service::list_with_filters_as_dto(
         filters.date_from,
         filters.date_to,
         filters.transaction_type,
         filters.operation_type_name,
         filters.posting_number,
+        active_org.scope(None),
+    )
+    .await
+    .context("Failed to list OZON transactions")?;
+
+    Ok(Json(serde_json::json!(transactions)))
+}
+
+const EXPORT_HEADERS: &[&str] = &[
+    "Date",
+    "Operation ID",
+    "Operation Type",
+    "Substatus",
+    "Delivering Date",
+    "Posting Number",
+    "Transaction Type",
+    "Delivery Schema",
+    "Amount",
+    "Accruals",
+    "Commission",
+    "Delivery",
+    "Post",
+];
+
+fn export_date(value: &str) -> String {
+    let date = value.split([' ', 'T']).next().unwrap_or(value);
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| d.format("%d.%m.%Y").to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+/// GET /api/a014/ozon-transactions/export?format=csv&... — все транзакции по фильтрам.
+/// Список фильтруется в памяти сервиса, поэтому поток режет готовые строки на чанки.
+pub async fn export(
+    active_org: ActiveOrganization,
+    axum::extract::Query(format): axum::extract::Query<
+        crate::shared::export::csv::ExportFormatQuery,
+    >,
+    axum::extract::Query(filters): axum::extract::Query<ListFilters>,
+) -> Result<axum::response::Response, (axum::http::StatusCode, String)> {
+    use crate::shared::export::csv::{self, decimal};
+
+    format.ensure_csv()?;
+    let filename = format!(
+        "ozon_transactions_{}_{}.csv",
+        filters.date_from.as_deref().unwrap_or("all"),
+        filters.date_to.as_deref().unwrap_or("all")
+    );
+    let transactions = a014_ozon_transactions::service::list_with_filters_as_dto(
+        filters.date_from,
+        filters.date_to,
+        filters.transaction_type,
+        filters.operation_type_name,
+        filters.posting_number,
+        active_org.scope(None),
     )
     .await
     .map_err(|e| {
-        tracing::error!("Failed to list OZON transactions: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        tracing::error!("Failed to export OZON transactions: {}", e);
+        (axum::http::StatusCode::INTERNAL_SERVER_ERROR, String::new())
     })?;
 
-    Ok(Json(serde_json::json!(transactions)))
+    let rows = transactions
+        .into_iter()
+        .map(|txn| {
+            vec![
+                export_date(&txn.operation_date),
+                txn.operation_id.to_string(),
+                txn.operation_type_name,
+                txn.substatus.unwrap_or_default(),
+                txn.delivering_date
+                    .as_deref()
+                    .map(export_date)
+                    .unwrap_or_default(),
+                txn.posting_number,
+                txn.transaction_type,
+                txn.delivery_schema,
+                decimal(txn.amount),
+                decimal(txn.accruals_for_sale),
+                decimal(txn.sale_commission),
+                decimal(txn.delivery_charge),
+                if txn.is_posted { "Да" } else { "Нет" }.to_string(),
+            ]
+        })
+        .collect();
+    Ok(csv::csv_response(
+        &filename,
+        EXPORT_HEADERS,
+        csv::from_rows(rows),
+    ))
+}
+
+/// Транзакция есть в текущей организации; транзакция другой организации — как
+/// несуществующая. Без выбранной организации документ не загружается.
+async fn ensure_visible(active_org: &ActiveOrganization, uuid: Uuid) -> Result<(), AppError> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    a014_ozon_transactions::service::get_by_id(uuid)
+        .await
+        .context("Failed to get OZON transaction by ID")?
+        .filter(|txn| active_org.allows(&txn.header.organization_id))
+        .ok_or_else(|| AppError::not_found("Документ не найден"))?;
+    Ok(())
 }
 
 /// Handler для получения транзакции по ID
 pub async fn get_by_id(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+) -> Result<Json<serde_json::Value>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
 
     let transaction = a014_ozon_transactions::service::get_by_id_as_dto(uuid)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get OZON transaction by ID: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+        .context("Failed to get OZON transaction by ID")?
+        .filter(|txn| active_org.allows(&txn.header.organization_id))
+        .ok_or_else(|| AppError::not_found("Документ не найден"))?;
 
     Ok(Json(serde_json::json!(transaction)))
 }
 
 /// Handler для удаления транзакции
 pub async fn delete(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+) -> Result<Json<serde_json::Value>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
+    ensure_visible(&active_org, uuid).await?;
 
     let deleted = a014_ozon_transactions::service::delete(uuid)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to delete OZON transaction: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to delete OZON transaction")?;
 
     if !deleted {
-        return Err(axum::http::StatusCode::NOT_FOUND);
+        return Err(AppError::not_found("Документ не найден"));
     }
 
     Ok(Json(serde_json::json!({"success": true})))
@@ -72,11 +172,12 @@ pub async fn delete(
 
 /// Handler для получения транзакций по posting_number
 pub async fn get_by_posting_number(
+    active_org: ActiveOrganization,
     axum::extract::Path(posting_number): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+) -> Result<Json<serde_json::Value>, AppError> {
     // Декодируем URL-кодированный posting_number
     let decoded_posting_number =
-        urlencoding::decode(&posting_number).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+        urlencoding::decode(&posting_number).map_err(|_| AppError::invalid_id(&posting_number))?;
 
     tracing::info!(
         "Getting transactions for posting_number: {} (original: {})",
@@ -84,13 +185,22 @@ pub async fn get_by_posting_number(
         posting_number
     );
 
-    let transactions =
+    let mut transactions =
         a014_ozon_transactions::service::get_by_posting_number_as_dto(&decoded_posting_number)
             .await
-            .map_err(|e| {
-                tracing::error!("Failed to get OZON transactions by posting_number: {}", e);
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .context("Failed to get OZON transactions by posting_number")?;
+    if active_org.0.is_some() {
+        // В DTO списка нет организации — сверяем по агрегатам
+        let visible: Vec<String> =
+            a014_ozon_transactions::repository::get_by_posting_number(&decoded_posting_number)
+                .await
+                .context("Failed to get OZON transactions by posting_number")?
+                .into_iter()
+                .filter(|txn| active_org.allows(&txn.header.organization_id))
+                .map(|txn| txn.to_string_id())
+                .collect();
+        transactions.retain(|txn| visible.contains(&txn.id));
+    }
 
     tracing::info!(
         "Found {} transactions for posting_number: {}",
@@ -102,62 +212,58 @@ pub async fn get_by_posting_number(
 
 /// Handler для проведения документа
 pub async fn post_document(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    Query(expected): Query<ExpectedVersion>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
+    ensure_visible(&active_org, uuid).await?;
 
-    a014_ozon_transactions::posting::post_document(uuid)
+    a014_ozon_transactions::posting::post_document_at_version(uuid, expected.version)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to post document: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .map_err(|e| optimistic_lock::command_error(e, "Failed to post document"))?;
 
     Ok(Json(serde_json::json!({"success": true})))
 }
 
 /// Handler для отмены проведения документа
 pub async fn unpost_document(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    Query(expected): Query<ExpectedVersion>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
+    ensure_visible(&active_org, uuid).await?;
 
-    a014_ozon_transactions::posting::unpost_document(uuid)
+    a014_ozon_transactions::posting::unpost_document_at_version(uuid, expected.version)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to unpost document: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .map_err(|e| optimistic_lock::command_error(e, "Failed to unpost document"))?;
 
     Ok(Json(serde_json::json!({"success": true})))
 }
 
 /// Handler для получения проекций по registrator_ref
 pub async fn get_projections(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+) -> Result<Json<serde_json::Value>, AppError> {
+    if active_org.0.is_some() {
+        let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
+        ensure_visible(&active_org, uuid).await?;
+    }
     // Получаем данные из всех проекций
     let p900_items = crate::projections::p900_mp_sales_register::service::get_by_registrator(&id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get p900 projections: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to get p900 projections")?;
 
     let p902_items =
         crate::projections::p902_ozon_finance_realization::repository::get_by_registrator(&id)
             .await
-            .map_err(|e| {
-                tracing::error!("Failed to get p902 projections: {}", e);
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .context("Failed to get p902 projections")?;
 
     let p904_items = crate::projections::p904_sales_data::repository::get_by_registrator(&id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get p904 projections: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to get p904 projections")?;
 
     // Объединяем результаты
     let result = serde_json::json!({
@@ -1,11 +1,22 @@
+use anyhow::Context;
 use axum::{extract::Query, Json};
 use contracts::domain::a015_wb_orders::aggregate::WbOrders;
 use contracts::domain::common::AggregateId;
+use contracts::shared::access::FinanceFields;
+use contracts::shared::marketplace_links::WithMarketplaceLinks;
+use contracts::system::auth::TokenClaims;
+use contracts::system::raw_storage::RawPayloadVersionDto;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::domain::a015_wb_orders;
 use crate::shared::data::raw_storage;
+use crate::shared::error::AppError;
+use crate::shared::marketplaces::links;
+use crate::shared::optimistic_lock::{self, ExpectedVersion};
+use crate::system::access::resolver::can_view_finance;
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::org_context::ActiveOrganization;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WbOrdersListItemDto {
@@ -28,6 +39,10 @@ pub struct ListOrdersQuery {
     pub sort_by: Option<String>,
     pub sort_desc: Option<bool>,
     pub show_cancelled: Option<bool>,
+    /// `true` — только отменённые, `false` — без отменённых
+    pub is_cancel: Option<bool>,
+    pub is_posted: Option<bool>,
+    pub warehouse: Option<String>,
 }
 
 /// Simplified DTO for list responses (from repository row data)
@@ -60,6 +75,22 @@ pub struct WbOrdersListItemSimpleDto {
     pub has_wb_sales: bool,
 }
 
+impl FinanceFields for WbOrdersListItemSimpleDto {
+    const FINANCE_FIELDS: &'static [&'static str] = &[
+        "margin_pro",
+        "dealer_price_ut",
+        "finished_price",
+        "total_price",
+    ];
+
+    fn mask_finance(&mut self) {
+        self.margin_pro = None;
+        self.dealer_price_ut = None;
+        self.finished_price = None;
+        self.total_price = None;
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct PaginatedWbOrdersResponse {
     pub items: Vec<WbOrdersListItemSimpleDto>,
@@ -69,74 +100,87 @@ pub struct PaginatedWbOrdersResponse {
     pub total_pages: usize,
 }
 
-/// Handler для получения списка Wildberries Orders с серверной пагинацией
-pub async fn list_orders(
-    Query(query): Query<ListOrdersQuery>,
-) -> Result<Json<PaginatedWbOrdersResponse>, axum::http::StatusCode> {
-    use a015_wb_orders::repository::{list_sql, WbOrdersListQuery};
+async fn finance_access(claims: &TokenClaims) -> Result<bool, AppError> {
+    Ok(can_view_finance(claims)
+        .await
+        .context("Failed to resolve finance access")?)
+}
 
-    let page_size = query.limit.unwrap_or(100);
-    let offset = query.offset.unwrap_or(0);
-    let page = if page_size > 0 { offset / page_size } else { 0 };
-    let sort_by = query
-        .sort_by
-        .clone()
-        .unwrap_or_else(|| "document_date".to_string());
-    let sort_desc = query.sort_desc.unwrap_or(true);
-    let show_cancelled = query.show_cancelled.unwrap_or(true);
+/// Без доступа к финансам сортировка по сумме раскрыла бы её порядок.
+fn restrict_finance_sort(query: &mut ListOrdersQuery, show_finance: bool) {
+    if !show_finance
+        && query
+            .sort_by
+            .as_deref()
+            .is_some_and(|field| WbOrdersListItemSimpleDto::FINANCE_FIELDS.contains(&field))
+    {
+        query.sort_by = None;
+    }
+}
 
-    // Build query for SQL-based list
-    let list_query = WbOrdersListQuery {
+/// Запрос к `list_sql` по фильтрам списка (общий для списка и выгрузки).
+fn build_list_query(
+    query: &ListOrdersQuery,
+    limit: usize,
+    offset: usize,
+    show_finance: bool,
+) -> Result<a015_wb_orders::repository::WbOrdersListQuery, AppError> {
+    // Строка поиска понимает быстрый фильтр: поля → условия, остальное — обычный поиск
+    let quick = crate::shared::quick_filter::build_where(
+        query.search_query.as_deref().unwrap_or_default(),
+        contracts::domain::a015_wb_orders::quick_filter::QUICK_FILTER_FIELDS,
+        a015_wb_orders::repository::QUICK_FILTER_COLUMNS,
+        show_finance,
+    )
+    .map_err(|e| AppError::bad_request(format!("Некорректный быстрый фильтр: {}", e)))?;
+
+    Ok(a015_wb_orders::repository::WbOrdersListQuery {
         date_from: query.date_from.clone(),
         date_to: query.date_to.clone(),
         organization_id: query.organization_id.clone(),
-        search_query: query.search_query.clone(),
-        sort_by: sort_by.clone(),
-        sort_desc,
-        limit: page_size,
+        search_query: quick.free_text,
+        quick_conditions: quick.conditions,
+        sort_by: query
+            .sort_by
+            .clone()
+            .unwrap_or_else(|| "document_date".to_string()),
+        sort_desc: query.sort_desc.unwrap_or(true),
+        limit,
         offset,
-        show_cancelled,
-    };
-
-    // Execute SQL query
-    let result = list_sql(list_query.clone()).await.map_err(|e| {
-        tracing::error!("Failed to list Wildberries orders: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+        show_cancelled: query.show_cancelled.unwrap_or(true),
+        is_cancel: query.is_cancel,
+        is_posted: query.is_posted,
+        warehouse: query.warehouse.clone(),
+    })
+}
 
-    let total = result.total;
-    let total_pages = if page_size > 0 {
-        (total + page_size - 1) / page_size
-    } else {
-        0
-    };
+/// Справочники для строк списка: товары МП и номенклатура.
+struct ListReferenceMaps {
+    mp_map: std::collections::HashMap<String, (String, Option<String>)>,
+    nom_map: std::collections::HashMap<String, (String, String, String, Option<String>)>,
+}
 
-    let marketplace_products = crate::domain::a007_marketplace_product::service::list_all()
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to load marketplace products: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+impl ListReferenceMaps {
+    async fn load() -> Result<Self, AppError> {
+        let marketplace_products = crate::domain::a007_marketplace_product::service::list_all()
+            .await
+            .context("Failed to load marketplace products")?;
 
-    let mp_map: std::collections::HashMap<String, (String, Option<String>)> = marketplace_products
-        .into_iter()
-        .map(|mp| {
-            (
-                mp.base.id.as_string(),
-                (mp.article.clone(), mp.nomenclature_ref.clone()),
-            )
-        })
-        .collect();
+        let mp_map = marketplace_products
+            .into_iter()
+            .map(|mp| {
+                (
+                    mp.base.id.as_string(),
+                    (mp.article.clone(), mp.nomenclature_ref.clone()),
+                )
+            })
+            .collect();
 
-    let nomenclature_items = crate::domain::a004_nomenclature::service::list_all()
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to load nomenclature: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        let nomenclature_items = crate::domain::a004_nomenclature::service::list_all()
+            .await
+            .context("Failed to load nomenclature")?;
 
-    let nom_map: std::collections::HashMap<String, (String, String, String, Option<String>)> =
-        nomenclature_items
+        let nom_map = nomenclature_items
             .into_iter()
             .map(|nom| {
                 (
@@ -151,90 +195,136 @@ pub async fn list_orders(
             })
             .collect();
 
+        Ok(Self { mp_map, nom_map })
+    }
+
+    fn to_list_item(
+        &self,
+        row: a015_wb_orders::repository::WbOrdersListRow,
+    ) -> WbOrdersListItemSimpleDto {
+        let (mp_map, nom_map) = (&self.mp_map, &self.nom_map);
+        let organization_name = row.organization_name.clone();
+
+        let (marketplace_article, nomenclature_ref_from_mp) = row
+            .marketplace_product_ref
+            .as_ref()
+            .and_then(|mp_ref| mp_map.get(mp_ref).cloned())
+            .unwrap_or((String::new(), None));
+
+        let nom_ref = row
+            .nomenclature_ref
+            .as_ref()
+            .or(nomenclature_ref_from_mp.as_ref());
+        let (nomenclature_code, nomenclature_article) = nom_ref
+            .and_then(|nr| {
+                nom_map
+                    .get(nr)
+                    .cloned()
+                    .map(|(code, article, _, _)| (code, article))
+            })
+            .unwrap_or((String::new(), String::new()));
+
+        let effective_base_ref = row.base_nomenclature_ref.clone().or_else(|| {
+            nom_ref.and_then(|nr| {
+                nom_map.get(nr).and_then(|(_, _, _, base_ref)| {
+                    base_ref
+                        .clone()
+                        .filter(|s| {
+                            let v = s.trim();
+                            !v.is_empty() && v != "00000000-0000-0000-0000-000000000000"
+                        })
+                        .or_else(|| Some(nr.to_string()))
+                })
+            })
+        });
+
+        let (base_nomenclature_article, base_nomenclature_description) = effective_base_ref
+            .as_ref()
+            .and_then(|base_ref| {
+                nom_map
+                    .get(base_ref)
+                    .cloned()
+                    .map(|(_, article, description, _)| (Some(article), Some(description)))
+            })
+            .or_else(|| {
+                row.base_nomenclature_article
+                    .clone()
+                    .map(|article| (Some(article), row.base_nomenclature_description.clone()))
+            })
+            .unwrap_or((None, None));
+
+        WbOrdersListItemSimpleDto {
+            id: row.id,
+            document_no: row.document_no,
+            line_id: row.line_id,
+            document_date: row.document_date,
+            supplier_article: row.supplier_article,
+            brand: row.brand,
+            qty: row.qty,
+            margin_pro: row.margin_pro,
+            dealer_price_ut: row.dealer_price_ut,
+            finished_price: row.finished_price,
+            total_price: row.total_price,
+            is_cancel: row.is_cancel,
+            is_supply: row.is_supply,
+            is_realization: row.is_realization,
+            warehouse_type: row.warehouse_type,
+            income_id: row.income_id,
+            currency_code: row.currency_code,
+            is_posted: row.is_posted,
+            organization_name,
+            marketplace_article: Some(marketplace_article),
+            nomenclature_code: Some(nomenclature_code),
+            nomenclature_article: Some(nomenclature_article),
+            base_nomenclature_article,
+            base_nomenclature_description,
+            has_wb_sales: row.has_wb_sales,
+        }
+    }
+}
+
+/// Handler для получения списка Wildberries Orders с серверной пагинацией
+pub async fn list_orders(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+    Query(mut query): Query<ListOrdersQuery>,
+) -> Result<Json<PaginatedWbOrdersResponse>, AppError> {
+    use a015_wb_orders::repository::list_sql;
+
+    query.organization_id = active_org.scope(query.organization_id.take());
+    let show_finance = finance_access(&claims).await?;
+    restrict_finance_sort(&mut query, show_finance);
+
+    let page_size = query.limit.unwrap_or(100);
+    let offset = query.offset.unwrap_or(0);
+    let page = if page_size > 0 { offset / page_size } else { 0 };
+
+    // Build query for SQL-based list
+    let list_query = build_list_query(&query, page_size, offset, show_finance)?;
+
+    // Execute SQL query
+    let result = list_sql(list_query)
+        .await
+        .context("Failed to list Wildberries orders")?;
+
+    let total = result.total;
+    let total_pages = if page_size > 0 {
+        (total + page_size - 1) / page_size
+    } else {
+        0
+    };
+
+    let maps = ListReferenceMaps::load().await?;
+
     // Build response DTOs with reference data
-    let items: Vec<WbOrdersListItemSimpleDto> = result
+    let mut items: Vec<WbOrdersListItemSimpleDto> = result
         .items
         .into_iter()
-        .map(|row| {
-            let organization_name = row.organization_name.clone();
-
-            let (marketplace_article, nomenclature_ref_from_mp) = row
-                .marketplace_product_ref
-                .as_ref()
-                .and_then(|mp_ref| mp_map.get(mp_ref).cloned())
-                .unwrap_or((String::new(), None));
-
-            let nom_ref = row
-                .nomenclature_ref
-                .as_ref()
-                .or(nomenclature_ref_from_mp.as_ref());
-            let (nomenclature_code, nomenclature_article) = nom_ref
-                .and_then(|nr| {
-                    nom_map
-                        .get(nr)
-                        .cloned()
-                        .map(|(code, article, _, _)| (code, article))
-                })
-                .unwrap_or((String::new(), String::new()));
-
-            let effective_base_ref = row.base_nomenclature_ref.clone().or_else(|| {
-                nom_ref.and_then(|nr| {
-                    nom_map.get(nr).and_then(|(_, _, _, base_ref)| {
-                        base_ref
-                            .clone()
-                            .filter(|s| {
-                                let v = s.trim();
-                                !v.is_empty() && v != "00000000-0000-0000-0000-000000000000"
-                            })
-                            .or_else(|| Some(nr.to_string()))
-                    })
-                })
-            });
-
-            let (base_nomenclature_article, base_nomenclature_description) = effective_base_ref
-                .as_ref()
-                .and_then(|base_ref| {
-                    nom_map
-                        .get(base_ref)
-                        .cloned()
-                        .map(|(_, article, description, _)| (Some(article), Some(description)))
-                })
-                .or_else(|| {
-                    row.base_nomenclature_article
-                        .clone()
-                        .map(|article| (Some(article), row.base_nomenclature_description.clone()))
-                })
-                .unwrap_or((None, None));
-
-            WbOrdersListItemSimpleDto {
-                id: row.id,
-                document_no: row.document_no,
-                line_id: row.line_id,
-                document_date: row.document_date,
-                supplier_article: row.supplier_article,
-                brand: row.brand,
-                qty: row.qty,
-                margin_pro: row.margin_pro,
-                dealer_price_ut: row.dealer_price_ut,
-                finished_price: row.finished_price,
-                total_price: row.total_price,
-                is_cancel: row.is_cancel,
-                is_supply: row.is_supply,
-                is_realization: row.is_realization,
-                warehouse_type: row.warehouse_type,
-                income_id: row.income_id,
-                currency_code: row.currency_code,
-                is_posted: row.is_posted,
-                organization_name,
-                marketplace_article: Some(marketplace_article),
-                nomenclature_code: Some(nomenclature_code),
-                nomenclature_article: Some(nomenclature_article),
-                base_nomenclature_article,
-                base_nomenclature_description,
-                has_wb_sales: row.has_wb_sales,
-            }
-        })
+        .map(|row| maps.to_list_item(row))
         .collect();
+    if !show_finance {
+        items.iter_mut().for_each(FinanceFields::mask_finance);
+    }
 
     Ok(Json(PaginatedWbOrdersResponse {
         items,
@@ -245,21 +335,139 @@ pub async fn list_orders(
     }))
 }
 
+const EXPORT_HEADERS: &[&str] = &[
+    "Номер заказа",
+    "Дата заказа",
+    "Организация",
+    "Артикул продавца",
+    "Артикул МП",
+    "Артикул 1С",
+    "Артикул база",
+    "Наименование база",
+    "Бренд",
+    "Маржа, %",
+    "Дил. цена УТ",
+    "Итоговая цена",
+    "Валюта",
+    "Отменён",
+];
+
+/// Код валюты (ISO 4217 numeric) для выгрузки; пусто — рублёвый заказ.
+fn currency_label(code: Option<i64>) -> &'static str {
+    match code {
+        Some(398) => "KZT",
+        Some(933) => "BYN",
+        Some(51) => "AMD",
+        Some(643) | None => "RUB",
+        Some(_) => "?",
+    }
+}
+
+fn export_row(item: WbOrdersListItemSimpleDto) -> Vec<String> {
+    use crate::shared::export::csv::decimal_opt;
+
+    let order_date = item
+        .document_date
+        .as_deref()
+        .and_then(|d| d.split('T').next())
+        .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .map(|d| d.format("%d.%m.%Y").to_string())
+        .or(item.document_date)
+        .unwrap_or_default();
+    vec![
+        item.document_no,
+        order_date,
+        item.organization_name.unwrap_or_default(),
+        item.supplier_article.unwrap_or_default(),
+        item.marketplace_article.unwrap_or_default(),
+        item.nomenclature_article.unwrap_or_default(),
+        item.base_nomenclature_article.unwrap_or_default(),
+        item.base_nomenclature_description.unwrap_or_default(),
+        item.brand.unwrap_or_default(),
+        decimal_opt(item.margin_pro),
+        decimal_opt(item.dealer_price_ut),
+        decimal_opt(item.finished_price),
+        currency_label(item.currency_code).to_string(),
+        if item.is_cancel.unwrap_or(false) {
+            "Да"
+        } else {
+            "Нет"
+        }
+        .to_string(),
+    ]
+}
+
+/// GET /api/a015/wb-orders/export?format=csv&... — все строки по фильтрам списка.
+pub async fn export_orders(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+    Query(format): Query<crate::shared::export::csv::ExportFormatQuery>,
+    Query(mut query): Query<ListOrdersQuery>,
+) -> Result<axum::response::Response, (axum::http::StatusCode, String)> {
+    use crate::shared::export::csv;
+
+    query.organization_id = active_org.scope(query.organization_id.take());
+    format.ensure_csv()?;
+    let show_finance = finance_access(&claims)
+        .await
+        .map_err(|e| (e.status(), String::new()))?;
+    restrict_finance_sort(&mut query, show_finance);
+    // Проверяем фильтр до начала ответа: ошибка в потоке уже не станет 400.
+    build_list_query(&query, 0, 0, show_finance).map_err(|e| (e.status(), String::new()))?;
+    let maps = std::sync::Arc::new(
+        ListReferenceMaps::load()
+            .await
+            .map_err(|e| (e.status(), String::new()))?,
+    );
+
+    let filename = format!(
+        "wb_orders_{}_{}.csv",
+        query.date_from.as_deref().unwrap_or("all"),
+        query.date_to.as_deref().unwrap_or("all")
+    );
+    let pages = csv::paged(move |offset, limit| {
+        let list_query = build_list_query(&query, limit, offset, show_finance);
+        let maps = maps.clone();
+        async move {
+            let list_query = list_query.map_err(|_| anyhow::anyhow!("invalid quick filter"))?;
+            let result = a015_wb_orders::repository::list_sql(list_query).await?;
+            Ok(result
+                .items
+                .into_iter()
+                .map(|row| {
+                    let mut item = maps.to_list_item(row);
+                    if !show_finance {
+                        item.mask_finance();
+                    }
+                    export_row(item)
+                })
+                .collect())
+        }
+    });
+    Ok(csv::csv_response(&filename, EXPORT_HEADERS, pages))
+}
+
 /// Handler для получения детальной информации о Wildberries Order
 pub async fn get_order_detail(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<WbOrders>, axum::http::StatusCode> {
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+) -> Result<Json<WithMarketplaceLinks<WbOrders>>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
 
-    let item = a015_wb_orders::service::get_by_id(uuid)
+    let mut item = a015_wb_orders::service::get_by_id(uuid)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get Wildberries order detail: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
-
-    Ok(Json(item))
+        .context("Failed to get Wildberries order detail")?
+        .filter(|item| active_org.allows(&item.header.organization_id))
+        .ok_or_else(|| AppError::not_found("Документ не найден"))?;
+    if !finance_access(&claims).await? {
+        item.mask_finance();
+    }
+
+    Ok(Json(WithMarketplaceLinks {
+        marketplace_links: links::wb_document_links(item.line.nm_id),
+        item,
+    }))
 }
 
 /// Handler для поиска документов по srid (document_no)
@@ -269,58 +477,116 @@ pub struct SearchBySridQuery {
 }
 
 pub async fn search_by_srid(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     Query(query): Query<SearchBySridQuery>,
-) -> Result<Json<Vec<WbOrders>>, axum::http::StatusCode> {
-    let items = a015_wb_orders::repository::search_by_document_no(&query.srid)
+) -> Result<Json<Vec<WbOrders>>, AppError> {
+    let mut items = a015_wb_orders::repository::search_by_document_no(&query.srid)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to search by srid: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to search by srid")?;
+    items.retain(|item| active_org.allows(&item.header.organization_id));
+    if !finance_access(&claims).await? {
+        items.iter_mut().for_each(FinanceFields::mask_finance);
+    }
 
     Ok(Json(items))
 }
 
-/// Handler для получения raw JSON от WB API по raw_payload_ref
+/// Заказ `id` есть в текущей организации; заказ другой организации — как
+/// несуществующий. Без выбранной организации документ не загружается.
+async fn ensure_visible(active_org: &ActiveOrganization, uuid: Uuid) -> Result<(), AppError> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    a015_wb_orders::service::get_by_id(uuid)
+        .await
+        .context("Failed to get Wildberries order")?
+        .filter(|item| active_org.allows(&item.header.organization_id))
+        .ok_or_else(|| AppError::not_found("Документ не найден"))?;
+    Ok(())
+}
+
+/// Raw JSON виден, только если заказ с его номером документа есть в текущей
+/// организации.
+async fn ensure_raw_visible(active_org: &ActiveOrganization, ref_id: &str) -> Result<(), AppError> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    let document_no = raw_storage::document_no_by_ref(ref_id)
+        .await
+        .context("Failed to resolve raw JSON document")?
+        .ok_or_else(|| AppError::not_found("Документ не найден"))?;
+    let orders = a015_wb_orders::repository::search_by_document_no(&document_no)
+        .await
+        .context("Failed to search by srid")?;
+    if orders
+        .iter()
+        .any(|order| active_org.allows(&order.header.organization_id))
+    {
+        Ok(())
+    } else {
+        Err(AppError::not_found("Документ не найден"))
+    }
+}
+
+/// Handler для получения raw JSON от WB API по raw_payload_ref.
+/// Сырой ответ API содержит суммы, поэтому без доступа к финансам не отдаётся.
 pub async fn get_raw_json(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     axum::extract::Path(ref_id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+) -> Result<Json<serde_json::Value>, AppError> {
+    if !finance_access(&claims).await? {
+        return Err(AppError::forbidden("Нет доступа к финансовым данным"));
+    }
+    ensure_raw_visible(&active_org, &ref_id).await?;
     let json_value = raw_storage::get_json_value_by_ref(&ref_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get raw JSON: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to get raw JSON")?;
 
     Ok(Json(json_value))
 }
 
+/// Handler для списка сохранённых версий raw JSON документа (история повторных загрузок)
+pub async fn get_raw_versions(
+    active_org: ActiveOrganization,
+    axum::extract::Path(ref_id): axum::extract::Path<String>,
+) -> Result<Json<Vec<RawPayloadVersionDto>>, AppError> {
+    ensure_raw_visible(&active_org, &ref_id).await?;
+    let versions = raw_storage::list_versions_by_ref(&ref_id)
+        .await
+        .context("Failed to list raw JSON versions")?;
+
+    Ok(Json(versions))
+}
+
 /// Handler для удаления документа
 pub async fn delete_order(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+) -> Result<Json<serde_json::Value>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
+    ensure_visible(&active_org, uuid).await?;
 
-    a015_wb_orders::service::delete(uuid).await.map_err(|e| {
-        tracing::error!("Failed to delete order: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    a015_wb_orders::service::delete(uuid)
+        .await
+        .context("Failed to delete order")?;
 
     Ok(Json(serde_json::json!({"success": true})))
 }
 
 /// Handler для проведения документа
 pub async fn post_order(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    Query(expected): Query<ExpectedVersion>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
+    ensure_visible(&active_org, uuid).await?;
 
-    a015_wb_orders::posting::post_document(uuid)
+    a015_wb_orders::posting::post_document_at_version(uuid, expected.version)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to post order: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .map_err(|e| optimistic_lock::command_error(e, "Failed to post order"))?;
 
     Ok(Json(
         serde_json::json!({"success": true, "message": "Document posted"}),
@@ -329,16 +595,16 @@ pub async fn post_order(
 
 /// Handler для отмены проведения документа
 pub async fn unpost_order(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    Query(expected): Query<ExpectedVersion>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
+    ensure_visible(&active_org, uuid).await?;
 
-    a015_wb_orders::posting::unpost_document(uuid)
+    a015_wb_orders::posting::unpost_document_at_version(uuid, expected.version)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to unpost order: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .map_err(|e| optimistic_lock::command_error(e, "Failed to unpost order"))?;
 
     Ok(Json(
         serde_json::json!({"success": true, "message": "Document unposted"}),
@@ -352,9 +618,11 @@ pub async fn unpost_order(
 /// Внимание: registrator_ref РАЗНЫЙ у двух проекций — p909 использует префиксный
 /// `a015:{id}`, p916 — «сырой» `{id}` (см. `a015::posting::post_document`).
 pub async fn get_projections(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+) -> Result<Json<serde_json::Value>, AppError> {
+    let uuid = Uuid::parse_str(&id).map_err(|_| AppError::invalid_id(&id))?;
+    ensure_visible(&active_org, uuid).await?;
     let raw_ref = uuid.to_string();
     let prefixed_ref = format!("a015:{raw_ref}");
 
@@ -363,10 +631,7 @@ pub async fn get_projections(
             &prefixed_ref,
         )
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get p909 projections for {}: {}", id, e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .with_context(|| format!("Failed to get p909 projections for {}", id))?;
 
     let p916_items =
         crate::projections::p916_mp_sales_funnel_turnovers::repository::list_by_registrator(
@@ -374,10 +639,7 @@ pub async fn get_projections(
             &raw_ref,
         )
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get p916 projections for {}: {}", id, e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .with_context(|| format!("Failed to get p916 projections for {}", id))?;
 
     Ok(Json(serde_json::json!({
         "p909_mp_order_line_turnovers": p909_items,
@@ -2,6 +2,7 @@ use axum::{extract::Query, Json};
 use chrono::NaiveDate;
 use contracts::domain::a016_ym_returns::aggregate::{YmReturn, YmReturnListItemDto};
 use contracts::domain::common::AggregateId;
+use contracts::shared::access::FinanceFields;
 use sea_orm::Statement;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -9,6 +10,9 @@ use uuid::Uuid;
 use crate::domain::a016_ym_returns;
 use crate::shared::data::db::get_connection;
 use crate::shared::data::raw_storage;
+use crate::system::access::resolver::can_view_finance;
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::org_context::ActiveOrganization;
 
 /// Серверные итоги по датасету
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,18 +51,35 @@ pub struct ListReturnsQuery {
     pub search_order_id: Option<String>,
 }
 
+async fn finance_access(
+    claims: &contracts::system::auth::TokenClaims,
+) -> Result<bool, axum::http::StatusCode> {
+    can_view_finance(claims).await.map_err(|e| {
+        tracing::error!("Failed to resolve finance access: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
 /// Handler для получения списка с пагинацией
 pub async fn list_returns(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     Query(query): Query<ListReturnsQuery>,
 ) -> Result<Json<PaginatedYmReturnsResponse>, axum::http::StatusCode> {
     use a016_ym_returns::repository::{list_sql, YmReturnsListQuery};
 
+    let show_finance = finance_access(&claims).await?;
+
     let page_size = query.limit.unwrap_or(100);
     let offset = query.offset.unwrap_or(0);
     let page = if page_size > 0 { offset / page_size } else { 0 };
+    // Без доступа к финансам сортировка по сумме раскрыла бы её порядок.
     let sort_by = query
         .sort_by
         .clone()
+        .filter(|field| {
+            show_finance || !YmReturnListItemDto::FINANCE_FIELDS.contains(&field.as_str())
+        })
         .unwrap_or_else(|| "created_at_source".to_string());
     let sort_desc = query.sort_desc.unwrap_or(true);
 
@@ -67,6 +88,7 @@ pub async fn list_returns(
         date_to: query.date_to.clone(),
         return_type: query.return_type.clone(),
         connection_mp_ref: query.connection_mp_ref.clone(),
+        organization_id: active_org.scope(None),
         search_return_id: query.search_return_id.clone(),
         search_order_id: query.search_order_id.clone(),
         sort_by: sort_by.clone(),
@@ -88,10 +110,17 @@ pub async fn list_returns(
     };
 
     // Рассчитать итоги по всему датасету (с учётом фильтров)
-    let totals = calculate_totals(&list_query).await.ok();
+    let mut totals = calculate_totals(&list_query).await.ok();
+    let mut items = result.items;
+    if !show_finance {
+        items.iter_mut().for_each(FinanceFields::mask_finance);
+        if let Some(totals) = totals.as_mut() {
+            totals.sum_amount = 0.0;
+        }
+    }
 
     Ok(Json(PaginatedYmReturnsResponse {
-        items: result.items,
+        items,
         total,
         page,
         page_size,
@@ -137,6 +166,12 @@ async fn calculate_totals(
             ));
         }
     }
+    if let Some(ref organization_id) = query.organization_id {
+        conditions.push(format!(
+            "LOWER(TRIM(REPLACE(COALESCE(json_extract(header_json, '$.organization_id'), ''), '\"', ''))) = LOWER(TRIM(REPLACE('{}', '\"', '')))",
+            organization_id.replace('\'', "''")
+        ));
+    }
     if let Some(ref search_return_id) = query.search_return_id {
         if !search_return_id.is_empty() {
             conditions.push(format!(
@@ -218,36 +253,102 @@ pub async fn get_source_order(
 }
 
 /// Handler для получения всех возвратов (без пагинации, для обратной совместимости)
-pub async fn list_returns_all() -> Result<Json<Vec<YmReturn>>, axum::http::StatusCode> {
-    let items = a016_ym_returns::service::list_all().await.map_err(|e| {
+pub async fn list_returns_all(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+) -> Result<Json<Vec<YmReturn>>, axum::http::StatusCode> {
+    let mut items = a016_ym_returns::service::list_all().await.map_err(|e| {
         tracing::error!("Failed to list Yandex Market returns: {}", e);
         axum::http::StatusCode::INTERNAL_SERVER_ERROR
     })?;
+    items.retain(|item| active_org.allows(&item.header.organization_id));
 
+    if !finance_access(&claims).await? {
+        items.iter_mut().for_each(FinanceFields::mask_finance);
+    }
     Ok(Json(items))
 }
 
 /// Handler для получения детальной информации о Yandex Market Return
 pub async fn get_return_detail(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<YmReturn>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
 
-    let item = a016_ym_returns::service::get_by_id(uuid)
+    let mut item = a016_ym_returns::service::get_by_id(uuid)
         .await
         .map_err(|e| {
             tracing::error!("Failed to get Yandex Market return detail: {}", e);
             axum::http::StatusCode::INTERNAL_SERVER_ERROR
         })?
+        .filter(|item| active_org.allows(&item.header.organization_id))
         .ok_or(axum::http::StatusCode::NOT_FOUND)?;
 
+    if !finance_access(&claims).await? {
+        item.mask_finance();
+    }
     Ok(Json(item))
 }
 
-/// Handler для получения raw JSON от Yandex Market API по raw_payload_ref
+/// Возврат есть в текущей организации; возврат другой организации — 404.
+/// Без выбранной организации документ не загружается.
+async fn ensure_visible(
+    active_org: &ActiveOrganization,
+    uuid: Uuid,
+) -> Result<(), axum::http::StatusCode> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    a016_ym_returns::service::get_by_id(uuid)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get Yandex Market return: {}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .filter(|item| active_org.allows(&item.header.organization_id))
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    Ok(())
+}
+
+/// Raw JSON возврата сохраняется с document_no = return_id; виден, только если
+/// возврат есть в текущей организации.
+async fn ensure_raw_visible(
+    active_org: &ActiveOrganization,
+    ref_id: &str,
+) -> Result<(), axum::http::StatusCode> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    let internal = |e: anyhow::Error| {
+        tracing::error!("Failed to resolve raw JSON document: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    };
+    let return_id = raw_storage::document_no_by_ref(ref_id)
+        .await
+        .map_err(internal)?
+        .and_then(|document_no| document_no.parse::<i64>().ok())
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    a016_ym_returns::repository::get_by_return_id(return_id)
+        .await
+        .map_err(internal)?
+        .filter(|item| active_org.allows(&item.header.organization_id))
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    Ok(())
+}
+
+/// Handler для получения raw JSON от Yandex Market API по raw_payload_ref.
+/// Сырой ответ API содержит суммы, поэтому без доступа к финансам не отдаётся.
 pub async fn get_raw_json(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     axum::extract::Path(ref_id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    if !finance_access(&claims).await? {
+        return Err(axum::http::StatusCode::FORBIDDEN);
+    }
+    ensure_raw_visible(&active_org, &ref_id).await?;
     let json_value = raw_storage::get_json_value_by_ref(&ref_id)
         .await
         .map_err(|e| {
@@ -260,9 +361,11 @@ pub async fn get_raw_json(
 
 /// Handler для проведения документа
 pub async fn post_document(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    ensure_visible(&active_org, uuid).await?;
 
     a016_ym_returns::posting::post_document(uuid)
         .await
@@ -276,9 +379,11 @@ pub async fn post_document(
 
 /// Handler для отмены проведения документа
 pub async fn unpost_document(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    ensure_visible(&active_org, uuid).await?;
 
     a016_ym_returns::posting::unpost_document(uuid)
         .await
@@ -298,6 +403,7 @@ pub struct PostPeriodRequest {
 
 /// Handler для проведения документов за период
 pub async fn post_period(
+    active_org: ActiveOrganization,
     Query(req): Query<PostPeriodRequest>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let from = NaiveDate::parse_from_str(&req.from, "%Y-%m-%d")
@@ -314,6 +420,13 @@ pub async fn post_period(
     let mut failed_count = 0;
 
     for doc in documents {
+        // Тестовые данные песочницы пакетно не проводятся
+        if doc.is_sandbox {
+            continue;
+        }
+        if !active_org.allows(&doc.header.organization_id) {
+            continue;
+        }
         let doc_date = doc.source_meta.fetched_at.date_naive();
         if doc_date >= from && doc_date <= to {
             match a016_ym_returns::posting::post_document(doc.base.id.value()).await {
@@ -336,91 +449,20 @@ pub async fn post_period(
     })))
 }
 
-#[derive(Deserialize)]
-pub struct BatchOperationRequest {
-    pub ids: Vec<String>,
-}
-
-/// Handler для пакетного проведения документов
-pub async fn batch_post_documents(
-    Json(req): Json<BatchOperationRequest>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let total = req.ids.len();
-    let mut succeeded = 0;
-    let mut failed = 0;
-
-    for id_str in req.ids {
-        let uuid = match Uuid::parse_str(&id_str) {
-            Ok(uuid) => uuid,
-            Err(_) => {
-                failed += 1;
-                continue;
-            }
-        };
-
-        match a016_ym_returns::posting::post_document(uuid).await {
-            Ok(_) => succeeded += 1,
-            Err(_) => failed += 1,
-        }
-    }
-
-    tracing::info!(
-        "Batch posted {} documents (succeeded: {}, failed: {})",
-        total,
-        succeeded,
-        failed
-    );
-
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "succeeded": succeeded,
-        "failed": failed,
-        "total": total
-    })))
-}
-
-/// Handler для пакетной отмены проведения документов
-pub async fn batch_unpost_documents(
-    Json(req): Json<BatchOperationRequest>,
-) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    let total = req.ids.len();
-    let mut succeeded = 0;
-    let mut failed = 0;
-
-    for id_str in req.ids {
-        let uuid = match Uuid::parse_str(&id_str) {
-            Ok(uuid) => uuid,
-            Err(_) => {
-                failed += 1;
-                continue;
-            }
-        };
-
-        match a016_ym_returns::posting::unpost_document(uuid).await {
-            Ok(_) => succeeded += 1,
-            Err(_) => failed += 1,
-        }
-    }
-
-    tracing::info!(
-        "Batch unposted {} documents (succeeded: {}, failed: {})",
-        total,
-        succeeded,
-        failed
-    );
-
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "succeeded": succeeded,
-        "failed": failed,
-        "total": total
-    })))
-}
-
 /// Handler для получения проекций по registrator_ref
 pub async fn get_projections(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    // Движения регистров — это суммы; без доступа к финансам не отдаются.
+    if !finance_access(&claims).await? {
+        return Err(axum::http::StatusCode::FORBIDDEN);
+    }
+    if active_org.0.is_some() {
+        let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+        ensure_visible(&active_org, uuid).await?;
+    }
     // Получаем данные из проекции p904 (YM Returns использует только её)
     let p904_items = crate::projections::p904_sales_data::repository::get_by_registrator(&id)
         .await
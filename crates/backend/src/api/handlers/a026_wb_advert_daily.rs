@@ -18,6 +18,7 @@ use crate::domain::a026_wb_advert_daily;
 use crate::domain::a026_wb_advert_daily::repository::{
     self as a026_repo, WbAdvertDailyListQuery, WbAdvertDailyListRow, WbAdvertDailyReportQuery,
 };
+use crate::system::org_context::ActiveOrganization;
 
 #[derive(Debug, Deserialize)]
 pub struct ListQuery {
@@ -157,6 +158,7 @@ pub struct WbAdvertDailyDetailsDto {
 }
 
 pub async fn list_paginated(
+    active_org: ActiveOrganization,
     Query(query): Query<ListQuery>,
 ) -> Result<Json<PaginatedResponse>, axum::http::StatusCode> {
     let page_size = query.limit.unwrap_or(100);
@@ -167,6 +169,7 @@ pub async fn list_paginated(
         date_from: query.date_from,
         date_to: query.date_to,
         connection_id: query.connection_id,
+        organization_id: active_org.scope(None),
         search_query: query.search_query,
         sort_by: query.sort_by.unwrap_or_else(|| "document_date".to_string()),
         sort_desc: query.sort_desc.unwrap_or(true),
@@ -205,11 +208,15 @@ fn csv_plain_error(status: StatusCode, message: impl Into<String>) -> Response {
 }
 
 /// Line-level CSV: одна строка = одна позиция документа `a026`. Разделитель `;`, UTF-8 BOM.
-pub async fn report_csv(Query(q): Query<ReportCsvQuery>) -> Response {
+pub async fn report_csv(
+    active_org: ActiveOrganization,
+    Query(q): Query<ReportCsvQuery>,
+) -> Response {
     let report_query = WbAdvertDailyReportQuery {
         date_from: q.date_from.clone(),
         date_to: q.date_to.clone(),
         connection_id: q.connection_id.clone(),
+        organization_id: active_org.scope(None),
         search_query: q.search_query.clone(),
     };
 
@@ -620,12 +627,13 @@ async fn build_doc_csv_row_prefix(
 }
 
 pub async fn get_by_id(
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
 ) -> Result<Json<WbAdvertDailyDetailsDto>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
     let doc = match a026_wb_advert_daily::service::get_by_id(uuid).await {
-        Ok(Some(doc)) => doc,
-        Ok(None) => return Err(axum::http::StatusCode::NOT_FOUND),
+        Ok(Some(doc)) if active_org.allows(&doc.header.organization_id) => doc,
+        Ok(_) => return Err(axum::http::StatusCode::NOT_FOUND),
         Err(e) => {
             tracing::error!("Failed to get WB advert daily document {}: {}", id, e);
             return Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
@@ -816,9 +824,32 @@ fn parse_uuid(value: &str) -> Option<Uuid> {
     Uuid::parse_str(value).ok()
 }
 
+/// Документ есть в текущей организации; документ другой организации — как
+/// несуществующий.
+async fn ensure_visible(
+    active_org: &ActiveOrganization,
+    id: &str,
+) -> Result<(), axum::http::StatusCode> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    let uuid = Uuid::parse_str(id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    a026_wb_advert_daily::service::get_by_id(uuid)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get WB advert daily document {}: {}", id, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .filter(|doc| active_org.allows(&doc.header.organization_id))
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    Ok(())
+}
+
 pub async fn post_document(
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    ensure_visible(&active_org, &id).await?;
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
 
     a026_wb_advert_daily::posting::post_document(uuid)
@@ -832,8 +863,10 @@ pub async fn post_document(
 }
 
 pub async fn unpost_document(
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    ensure_visible(&active_org, &id).await?;
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
 
     a026_wb_advert_daily::posting::unpost_document(uuid)
@@ -847,8 +880,10 @@ pub async fn unpost_document(
 }
 
 pub async fn get_projections(
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    ensure_visible(&active_org, &id).await?;
     let p913_items =
         crate::projections::p913_wb_advert_order_attr::repository::list_by_registrator(
             "a026_wb_advert_daily",
@@ -887,8 +922,10 @@ pub async fn get_projections(
 }
 
 pub async fn get_general_ledger_entries(
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    ensure_visible(&active_org, &id).await?;
     let rows = crate::general_ledger::repository::list_by_registrator("a026_wb_advert_daily", &id)
         .await
         .map_err(|e| {
@@ -573,6 +573,16 @@ pub async fn post_document(
             StatusCode::INTERNAL_SERVER_ERROR
         })?
         .ok_or(StatusCode::NOT_FOUND)?;
+    crate::system::document_audit::record(
+        "a027_wb_documents",
+        &id,
+        contracts::system::document_audit::DocumentAuditAction::Posted,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to record posting of WB document {}: {}", id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
     build_details_dto(doc).await.map(Json).map_err(|e| {
         tracing::error!("Failed to post WB document {}: {}", id, e);
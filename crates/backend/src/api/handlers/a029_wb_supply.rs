@@ -6,6 +6,7 @@ use uuid::Uuid;
 
 use crate::domain::a029_wb_supply;
 use crate::shared::data::raw_storage;
+use crate::system::org_context::ActiveOrganization;
 
 const ZERO_UUID: &str = "00000000-0000-0000-0000-000000000000";
 
@@ -214,6 +215,7 @@ pub struct WbSupplyLinkDto {
 }
 
 pub async fn list_supplies(
+    active_org: ActiveOrganization,
     Query(query): Query<ListSuppliesQuery>,
 ) -> Result<Json<PaginatedWbSupplyResponse>, axum::http::StatusCode> {
     use a029_wb_supply::repository::{list_sql, WbSupplyListQuery};
@@ -232,7 +234,7 @@ pub async fn list_supplies(
         date_from: query.date_from.clone(),
         date_to: query.date_to.clone(),
         connection_id: query.connection_id.clone(),
-        organization_id: query.organization_id.clone(),
+        organization_id: active_org.scope(query.organization_id.clone()),
         search_query: query.search_query.clone(),
         sort_by,
         sort_desc,
@@ -284,15 +286,18 @@ pub async fn list_supplies(
 
 /// Resolve a supply by either its internal UUID or WB supply ID ("WB-GI-…").
 /// Tabs now use WB-GI-... as key, so all per-supply endpoints must support both forms.
-async fn resolve_supply(id: &str) -> Result<WbSupply, axum::http::StatusCode> {
-    if id.starts_with("WB-") {
+/// A supply of another organization than the active one is reported as not found.
+async fn resolve_supply(
+    id: &str,
+    active_org: &ActiveOrganization,
+) -> Result<WbSupply, axum::http::StatusCode> {
+    let supply = if id.starts_with("WB-") {
         a029_wb_supply::service::get_by_supply_id(id)
             .await
             .map_err(|e| {
                 tracing::error!("resolve_supply by supply_id {}: {}", id, e);
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR
             })?
-            .ok_or(axum::http::StatusCode::NOT_FOUND)
     } else {
         let uuid = Uuid::parse_str(id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
         a029_wb_supply::service::get_by_id(uuid)
@@ -301,14 +306,17 @@ async fn resolve_supply(id: &str) -> Result<WbSupply, axum::http::StatusCode> {
                 tracing::error!("resolve_supply by uuid {}: {}", id, e);
                 axum::http::StatusCode::INTERNAL_SERVER_ERROR
             })?
-            .ok_or(axum::http::StatusCode::NOT_FOUND)
-    }
+    };
+    supply
+        .filter(|supply| active_org.allows(&supply.header.organization_id))
+        .ok_or(axum::http::StatusCode::NOT_FOUND)
 }
 
 pub async fn get_supply_detail(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<WbSupply>, axum::http::StatusCode> {
-    let mut item = resolve_supply(&id).await?;
+    let mut item = resolve_supply(&id, &active_org).await?;
 
     if item.supply_orders.is_empty() {
         item.supply_orders = orders_from_a015(&item.header.supply_id).await;
@@ -323,6 +331,7 @@ pub async fn get_supply_detail(
 /// Returns the full aggregate — used when navigating from orders list where only WB ID is known.
 /// If no stored orders, falls back to a015_wb_orders matched by income_id.
 pub async fn get_supply_by_wb_id(
+    active_org: ActiveOrganization,
     axum::extract::Path(wb_id): axum::extract::Path<String>,
 ) -> Result<Json<WbSupply>, axum::http::StatusCode> {
     let mut item = a029_wb_supply::service::get_by_supply_id(&wb_id)
@@ -331,6 +340,7 @@ pub async fn get_supply_by_wb_id(
             tracing::error!("Failed to get WB supply by wb_id {}: {}", wb_id, e);
             axum::http::StatusCode::INTERNAL_SERVER_ERROR
         })?
+        .filter(|item| active_org.allows(&item.header.organization_id))
         .ok_or(axum::http::StatusCode::NOT_FOUND)?;
 
     if item.supply_orders.is_empty() {
@@ -343,6 +353,7 @@ pub async fn get_supply_by_wb_id(
 }
 
 pub async fn get_supply_for_order(
+    active_org: ActiveOrganization,
     axum::extract::Path(order_id): axum::extract::Path<String>,
 ) -> Result<Json<WbSupplyLinkDto>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&order_id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
@@ -356,6 +367,7 @@ pub async fn get_supply_for_order(
             );
             axum::http::StatusCode::INTERNAL_SERVER_ERROR
         })?
+        .filter(|order| active_org.allows(&order.header.organization_id))
         .ok_or(axum::http::StatusCode::NOT_FOUND)?;
 
     let supply = a029_wb_supply::service::get_for_order(&order)
@@ -364,6 +376,7 @@ pub async fn get_supply_for_order(
             tracing::error!("Failed to find WB supply for order {}: {}", order_id, e);
             axum::http::StatusCode::INTERNAL_SERVER_ERROR
         })?
+        .filter(|supply| active_org.allows(&supply.header.organization_id))
         .ok_or(axum::http::StatusCode::NOT_FOUND)?;
 
     Ok(Json(WbSupplyLinkDto {
@@ -376,9 +389,10 @@ pub async fn get_supply_for_order(
 /// Returns the stored supply orders from the aggregate (no live WB call).
 /// Falls back to a015_wb_orders by income_id if no orders are stored in the supply.
 pub async fn get_supply_orders(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<Vec<WbSupplyOrderRow>>, axum::http::StatusCode> {
-    let supply = resolve_supply(&id).await?;
+    let supply = resolve_supply(&id, &active_org).await?;
 
     let mut orders = if supply.supply_orders.is_empty() {
         orders_from_a015(&supply.header.supply_id).await
@@ -434,10 +448,11 @@ pub struct StickersResponse {
 }
 
 pub async fn get_supply_stickers(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
     Query(query): Query<StickersQuery>,
 ) -> Result<Json<StickersResponse>, axum::http::StatusCode> {
-    let mut supply = resolve_supply(&id).await?;
+    let mut supply = resolve_supply(&id, &active_org).await?;
 
     // Fall back to a015_wb_orders if no stored orders in supply aggregate
     if supply.supply_orders.is_empty() {
@@ -587,8 +602,20 @@ pub async fn get_supply_stickers(
 }
 
 pub async fn get_raw_json(
+    active_org: ActiveOrganization,
     axum::extract::Path(ref_id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    if active_org.0.is_some() {
+        // raw JSON поставки сохраняется с document_no = WB supply ID
+        let supply_id = raw_storage::document_no_by_ref(&ref_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to resolve raw JSON document: {}", e);
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            })?
+            .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+        resolve_supply(&supply_id, &active_org).await?;
+    }
     let json_value = raw_storage::get_json_value_by_ref(&ref_id)
         .await
         .map_err(|e| {
@@ -600,9 +627,11 @@ pub async fn get_raw_json(
 }
 
 pub async fn delete_supply(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    resolve_supply(&id, &active_org).await?;
 
     a029_wb_supply::service::delete(uuid).await.map_err(|e| {
         tracing::error!("Failed to delete supply: {}", e);
@@ -6,6 +6,7 @@ use uuid::Uuid;
 
 use crate::domain::a002_organization;
 use crate::domain::a032_wb_returns_claims;
+use crate::system::org_context::ActiveOrganization;
 
 #[derive(Debug, Deserialize)]
 pub struct ListReturnsClaimsQuery {
@@ -19,6 +20,7 @@ pub struct ListReturnsClaimsQuery {
 
 /// GET /api/a032/wb-returns-claims
 pub async fn list_returns_claims(
+    active_org: ActiveOrganization,
     Query(_query): Query<ListReturnsClaimsQuery>,
 ) -> Result<
     Json<Vec<contracts::domain::a032_wb_returns_claims::aggregate::WbReturnsClaimsListDto>>,
@@ -52,6 +54,7 @@ pub async fn list_returns_claims(
 
     let dtos: Vec<_> = items
         .into_iter()
+        .filter(|a| active_org.allows(&a.organization_id))
         .map(|a| {
             let mut dto = a.to_list_dto();
             dto.org_name = org_name_map.get(&a.organization_id).cloned();
@@ -64,6 +67,7 @@ pub async fn list_returns_claims(
 
 /// GET /api/a032/wb-returns-claims/:id
 pub async fn get_returns_claim_detail(
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
 ) -> Result<
     Json<contracts::domain::a032_wb_returns_claims::aggregate::WbReturnsClaimsDetailDto>,
@@ -71,8 +75,10 @@ pub async fn get_returns_claim_detail(
 > {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
     match a032_wb_returns_claims::service::get_by_id(uuid).await {
-        Ok(Some(item)) => Ok(Json(item.to_detail_dto())),
-        Ok(None) => Err(axum::http::StatusCode::NOT_FOUND),
+        Ok(Some(item)) if active_org.allows(&item.organization_id) => {
+            Ok(Json(item.to_detail_dto()))
+        }
+        Ok(_) => Err(axum::http::StatusCode::NOT_FOUND),
         Err(e) => {
             tracing::error!("Failed to get wb returns claim {}: {}", id, e);
             Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::domain::a033_wb_day_close::{advert_builder, repository::ListQuery, service};
+use crate::system::org_context::ActiveOrganization;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Query params
@@ -27,6 +28,7 @@ pub struct ListQuery_ {
 // ─────────────────────────────────────────────────────────────────────────────
 
 pub async fn list_paginated(
+    active_org: ActiveOrganization,
     Query(q): Query<ListQuery_>,
 ) -> Result<Json<Vec<WbDayCloseListDto>>, axum::http::StatusCode> {
     let query = ListQuery {
@@ -34,6 +36,7 @@ pub async fn list_paginated(
         date_from: q.date_from,
         date_to: q.date_to,
         include_archived: q.include_archived,
+        organization_id: active_org.scope(None),
         limit: q.limit,
         offset: q.offset,
     };
@@ -47,6 +50,48 @@ pub async fn list_paginated(
         })
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Организация кабинета
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Кабинет маркетплейса принадлежит текущей организации. Закрытие дня
+/// организацию не хранит — она берётся из кабинета.
+async fn ensure_connection_visible(
+    active_org: &ActiveOrganization,
+    connection_id: &str,
+) -> Result<(), axum::http::StatusCode> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    let uuid = Uuid::parse_str(connection_id).map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
+    crate::domain::a006_connection_mp::service::get_by_id(uuid)
+        .await
+        .map_err(|e| {
+            tracing::error!("get a006 {}: {}", connection_id, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .filter(|connection| active_org.allows(&connection.organization_ref))
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    Ok(())
+}
+
+/// Документ кабинета текущей организации; документ другой организации — как
+/// несуществующий.
+async fn load_visible(
+    active_org: &ActiveOrganization,
+    uuid: Uuid,
+) -> Result<WbDayClose, axum::http::StatusCode> {
+    let doc = service::get_by_id(uuid)
+        .await
+        .map_err(|e| {
+            tracing::error!("get a033 {}: {}", uuid, e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    ensure_connection_visible(active_org, &doc.connection_id).await?;
+    Ok(doc)
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // GET /api/a033/wb-day-close/:id
 // ─────────────────────────────────────────────────────────────────────────────
@@ -82,16 +127,11 @@ pub struct RegistratorRow {
 }
 
 pub async fn advert_live(
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
 ) -> Result<Json<AdvertLiveTotals>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
-    let doc = service::get_by_id(uuid)
-        .await
-        .map_err(|e| {
-            tracing::error!("advert_live get a033 {}: {}", id, e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+    let doc = load_visible(&active_org, uuid).await?;
 
     let (build_result, diag) = tokio::try_join!(
         advert_builder::build(&doc.connection_id, &doc.business_date),
@@ -134,16 +174,12 @@ pub async fn advert_live(
     }))
 }
 
-pub async fn get_by_id(Path(id): Path<String>) -> Result<Json<WbDayClose>, axum::http::StatusCode> {
+pub async fn get_by_id(
+    active_org: ActiveOrganization,
+    Path(id): Path<String>,
+) -> Result<Json<WbDayClose>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
-    match service::get_by_id(uuid).await {
-        Ok(Some(doc)) => Ok(Json(doc)),
-        Ok(None) => Err(axum::http::StatusCode::NOT_FOUND),
-        Err(e) => {
-            tracing::error!("get a033 {}: {}", id, e);
-            Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    }
+    load_visible(&active_org, uuid).await.map(Json)
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -151,8 +187,10 @@ pub async fn get_by_id(Path(id): Path<String>) -> Result<Json<WbDayClose>, axum:
 // ─────────────────────────────────────────────────────────────────────────────
 
 pub async fn list_by_day(
+    active_org: ActiveOrganization,
     Path((connection_id, business_date)): Path<(String, String)>,
 ) -> Result<Json<Vec<WbDayCloseListDto>>, axum::http::StatusCode> {
+    ensure_connection_visible(&active_org, &connection_id).await?;
     service::list_by_day(&connection_id, &business_date)
         .await
         .map(|items| Json(items.into_iter().map(|d| d.to_list_dto()).collect()))
@@ -172,8 +210,10 @@ pub async fn list_by_day(
 // ─────────────────────────────────────────────────────────────────────────────
 
 pub async fn create_active(
+    active_org: ActiveOrganization,
     Json(body): Json<CreateActiveRequest>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    ensure_connection_visible(&active_org, &body.connection_id).await?;
     service::create_active(&body.connection_id, &body.business_date)
         .await
         .map(|id| Json(serde_json::json!({ "id": id.to_string() })))
@@ -188,9 +228,11 @@ pub async fn create_active(
 // ─────────────────────────────────────────────────────────────────────────────
 
 pub async fn recalculate(
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
 ) -> Result<axum::http::StatusCode, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    load_visible(&active_org, uuid).await?;
     service::recalculate(uuid).await.map_err(|e| {
         tracing::error!("recalculate a033 {}: {}", id, e);
         axum::http::StatusCode::INTERNAL_SERVER_ERROR
@@ -203,10 +245,12 @@ pub async fn recalculate(
 // ─────────────────────────────────────────────────────────────────────────────
 
 pub async fn repost_problematic_a012(
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
     Json(body): Json<RepostProblematicRequest>,
 ) -> Result<Json<RepostResult>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    load_visible(&active_org, uuid).await?;
     service::repost_problematic_a012(uuid, &body.only_problem_codes)
         .await
         .map(Json)
@@ -221,10 +265,12 @@ pub async fn repost_problematic_a012(
 // ─────────────────────────────────────────────────────────────────────────────
 
 pub async fn archive_and_recreate(
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
     Json(body): Json<ArchiveAndRecreateRequest>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     let uuid = Uuid::parse_str(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    load_visible(&active_org, uuid).await?;
     service::archive_and_recreate(uuid, body.reason)
         .await
         .map(|new_id| Json(serde_json::json!({ "id": new_id.to_string() })))
@@ -239,12 +285,15 @@ pub async fn archive_and_recreate(
 // ─────────────────────────────────────────────────────────────────────────────
 
 pub async fn compare(
+    active_org: ActiveOrganization,
     Json(body): Json<CompareRequest>,
 ) -> Result<Json<CompareResponse>, axum::http::StatusCode> {
     let active_uuid =
         Uuid::parse_str(&body.active_id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
     let archived_uuid =
         Uuid::parse_str(&body.archived_id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    load_visible(&active_org, active_uuid).await?;
+    load_visible(&active_org, archived_uuid).await?;
 
     service::compare(active_uuid, archived_uuid)
         .await
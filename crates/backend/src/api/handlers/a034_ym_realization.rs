@@ -94,6 +94,8 @@ pub struct YmRealizationDetailsDto {
     pub created_at: String,
     pub updated_at: String,
     pub is_posted: bool,
+    /// Загружен через подключение в режиме песочницы
+    pub is_sandbox: bool,
     /// Строки-продажи и строки-возвраты — раздельно (физически не смешиваются).
     pub sales_lines: Vec<YmRealizationLine>,
     pub return_lines: Vec<YmRealizationLine>,
@@ -273,6 +275,7 @@ async fn build_details_dto(doc: YmRealization) -> anyhow::Result<YmRealizationDe
         created_at: doc.base.metadata.created_at.to_rfc3339(),
         updated_at: doc.base.metadata.updated_at.to_rfc3339(),
         is_posted: doc.is_posted || doc.base.metadata.is_posted,
+        is_sandbox: doc.is_sandbox,
         sales_lines: doc.sales_lines,
         return_lines: doc.return_lines,
         product_names,
@@ -15,9 +15,38 @@ use contracts::dashboards::d406_wb_sales_funnel::{
     FunnelOrderChannel, WbSalesFunnelConversions, WbSalesFunnelMetrics, WbSalesFunnelOrderItem,
     WbSalesFunnelOrdersResponse, WbSalesFunnelRequest, WbSalesFunnelResponse, WbSalesFunnelRow,
 };
+use contracts::dashboards::d407_wb_supply_acceptance::{
+    is_flagged, shortfall as supply_shortfall, WbSupplyAcceptanceRequest,
+    WbSupplyAcceptanceResponse, WbSupplyAcceptanceRow, WbSupplyAcceptanceTotals,
+    DEFAULT_SHORTFALL_THRESHOLD,
+};
+use contracts::dashboards::d408_pnl_statement::{
+    months_in_period, pnl_effect, source_layer, PnlLine, PnlLineRow, PnlOrganizationSection,
+    PnlStatementRequest, PnlStatementResponse, PnlTurnoverRow,
+};
+use contracts::dashboards::d409_margin_scenario::{
+    apply_assumptions, MarginScenarioRequest, MarginScenarioResponse, MarginScenarioRow,
+    MarginScenarioTotals, MAX_SCENARIO_ITEMS,
+};
+use contracts::dashboards::d410_sku_launch_cohorts::{
+    accumulate, add_months, month_offset, payback_offset, CohortMonthCell, SkuLaunchCohort,
+    SkuLaunchCohortsRequest, SkuLaunchCohortsResponse,
+};
+use contracts::dashboards::d412_demand_forecast::{
+    error_metrics, DemandForecastPoint, DemandForecastProductRow, DemandForecastRequest,
+    DemandForecastResponse, ForecastMethod, PRODUCT_SUMMARY_WEEKS,
+};
+use contracts::dashboards::d414_import_coverage::{
+    ImportCoverageRequest, ImportCoverageResponse,
+};
+use contracts::dashboards::d415_sku_pnl::{SkuPnlRequest, SkuPnlResponse};
+use contracts::domain::a007_marketplace_product::aggregate::{AbcClass, XyzClass};
 use contracts::projections::p916_mp_sales_funnel_turnovers::dto::MpFunnelListRequest;
+use contracts::shared::analytics::margin::UnitEconomics;
 use serde::Deserialize;
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use crate::system::org_context::ActiveOrganization;
 
 #[derive(Deserialize)]
 pub struct WbOrderFlowQuery {
@@ -83,9 +112,43 @@ fn sql_lit(value: &str) -> String {
     format!("'{}'", value.replace('\'', "''"))
 }
 
+/// Кабинет из колонки `column` принадлежит организации. Организации в агрегатах
+/// дашбордов нет — она берётся из кабинета маркетплейса (a006).
+fn organization_connection_condition(column: &str, organization_ref: &str) -> String {
+    format!(
+        "{column} IN (SELECT id FROM a006_connection_mp \
+         WHERE LOWER(TRIM(REPLACE(COALESCE(organization_ref, ''), '\"', ''))) \
+         = LOWER(TRIM(REPLACE({}, '\"', ''))))",
+        sql_lit(organization_ref)
+    )
+}
+
+/// Кабинеты активной организации; `None` — организация не выбрана, видны все.
+async fn organization_connections(
+    active_org: &ActiveOrganization,
+) -> Result<Option<HashSet<String>>, axum::http::StatusCode> {
+    if active_org.0.is_none() {
+        return Ok(None);
+    }
+    let connections = crate::domain::a006_connection_mp::service::list_all()
+        .await
+        .map_err(|error| {
+            tracing::error!("list a006 for active organization failed: {}", error);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(Some(
+        connections
+            .into_iter()
+            .filter(|connection| active_org.allows(&connection.organization_ref))
+            .map(|connection| connection.base.id.as_string())
+            .collect(),
+    ))
+}
+
 fn push_common_filters(
     conditions: &mut Vec<String>,
     filters: &WbAdvertReportRequest,
+    organization_ref: Option<&str>,
     campaign_column: &str,
 ) {
     if let Some(value) = filters
@@ -112,6 +175,9 @@ fn push_common_filters(
     {
         conditions.push(format!("connection_mp_ref = {}", sql_lit(value)));
     }
+    if let Some(value) = organization_ref {
+        conditions.push(organization_connection_condition("connection_mp_ref", value));
+    }
     if let Some(value) = filters
         .wb_advert_campaign_code
         .as_deref()
@@ -124,11 +190,17 @@ fn push_common_filters(
 
 async fn fetch_p913_advert_report_rows(
     filters: &WbAdvertReportRequest,
+    organization_ref: Option<&str>,
 ) -> anyhow::Result<Vec<P913AdvertReportRow>> {
     use sea_orm::{ConnectionTrait, Statement};
 
     let mut conditions = Vec::new();
-    push_common_filters(&mut conditions, filters, "wb_advert_campaign_code");
+    push_common_filters(
+        &mut conditions,
+        filters,
+        organization_ref,
+        "wb_advert_campaign_code",
+    );
     let where_clause = if conditions.is_empty() {
         String::new()
     } else {
@@ -168,11 +240,17 @@ async fn fetch_p913_advert_report_rows(
 
 async fn fetch_p911_advert_report_rows(
     filters: &WbAdvertReportRequest,
+    organization_ref: Option<&str>,
 ) -> anyhow::Result<Vec<P911AdvertReportRow>> {
     use sea_orm::{ConnectionTrait, Statement};
 
     let mut conditions = vec!["turnover_code = 'advert_clicks_no_order'".to_string()];
-    push_common_filters(&mut conditions, filters, "wb_advert_campaign_code");
+    push_common_filters(
+        &mut conditions,
+        filters,
+        organization_ref,
+        "wb_advert_campaign_code",
+    );
     let sql = format!(
         "SELECT \
             COALESCE(wb_advert_campaign_code, '') AS campaign_code, \
@@ -457,15 +535,17 @@ fn to_report_node(
 
 /// GET /api/dashboards/wb-advert-report
 pub async fn wb_advert_report(
+    active_org: ActiveOrganization,
     Query(filters): Query<WbAdvertReportRequest>,
 ) -> Result<Json<WbAdvertReportResponse>, axum::http::StatusCode> {
-    let p913_rows = fetch_p913_advert_report_rows(&filters)
+    let organization_ref = active_org.0.as_deref();
+    let p913_rows = fetch_p913_advert_report_rows(&filters, organization_ref)
         .await
         .map_err(|error| {
             tracing::error!("wb_advert_report p913 aggregation failed: {}", error);
             axum::http::StatusCode::INTERNAL_SERVER_ERROR
         })?;
-    let p911_rows = fetch_p911_advert_report_rows(&filters)
+    let p911_rows = fetch_p911_advert_report_rows(&filters, organization_ref)
         .await
         .map_err(|error| {
             tracing::error!("wb_advert_report p911 aggregation failed: {}", error);
@@ -572,8 +652,1011 @@ pub async fn wb_advert_report(
     }))
 }
 
+struct SupplyHead {
+    supply_ref: String,
+    supply_id: String,
+    supply_name: Option<String>,
+    created_at_wb: Option<String>,
+    connection_mp_ref: String,
+}
+
+#[derive(Default)]
+struct SupplyAcceptanceAccum {
+    article: String,
+    shipped_qty: i64,
+    accepted_qty: i64,
+    sold_qty: i64,
+}
+
+/// Условия отбора поставок a029 для D407. Учитываются только поставки с ID
+/// вида `WB-GI-{income_id}` — только их можно сопоставить с заказами a015.
+fn supply_acceptance_conditions(
+    filters: &WbSupplyAcceptanceRequest,
+    organization_ref: Option<&str>,
+) -> String {
+    let mut conditions = vec![
+        "s.is_deleted = 0".to_string(),
+        "s.supply_id LIKE 'WB-GI-%'".to_string(),
+    ];
+    if let Some(value) = filters
+        .date_from
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        conditions.push(format!(
+            "SUBSTR(s.created_at_wb, 1, 10) >= {}",
+            sql_lit(value)
+        ));
+    }
+    if let Some(value) = filters
+        .date_to
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        conditions.push(format!(
+            "SUBSTR(s.created_at_wb, 1, 10) <= {}",
+            sql_lit(value)
+        ));
+    }
+    if let Some(value) = filters
+        .connection_mp_ref
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        conditions.push(format!("s.connection_id = {}", sql_lit(value)));
+    }
+    if let Some(value) = organization_ref {
+        conditions.push(organization_connection_condition("s.connection_id", value));
+    }
+    conditions.join(" AND ")
+}
+
+/// GET /api/dashboards/wb-supply-acceptance
+/// Расхождения приёмки (D407): отгружено по поставке vs принято/продано по данным WB.
+pub async fn wb_supply_acceptance(
+    active_org: ActiveOrganization,
+    Query(filters): Query<WbSupplyAcceptanceRequest>,
+) -> Result<Json<WbSupplyAcceptanceResponse>, axum::http::StatusCode> {
+    build_supply_acceptance(filters, active_org.0.as_deref())
+        .await
+        .map(Json)
+        .map_err(|error| {
+            tracing::error!("wb_supply_acceptance failed: {}", error);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+async fn build_supply_acceptance(
+    filters: WbSupplyAcceptanceRequest,
+    organization_ref: Option<&str>,
+) -> anyhow::Result<WbSupplyAcceptanceResponse> {
+    use sea_orm::{ConnectionTrait, Statement};
+
+    let db = crate::shared::data::db::get_connection();
+    let conditions = supply_acceptance_conditions(&filters, organization_ref);
+    let threshold = filters
+        .threshold
+        .unwrap_or(DEFAULT_SHORTFALL_THRESHOLD)
+        .max(0);
+
+    let supplies_sql = format!(
+        "SELECT s.id, s.supply_id, s.supply_name, s.created_at_wb, s.connection_id, \
+                CAST(SUBSTR(s.supply_id, 7) AS INTEGER) AS income_id \
+         FROM a029_wb_supply s \
+         WHERE {conditions}"
+    );
+    let mut supplies = HashMap::<String, SupplyHead>::new();
+    let mut supply_by_income = HashMap::<i64, String>::new();
+    for row in db
+        .query_all(Statement::from_string(
+            sea_orm::DatabaseBackend::Sqlite,
+            supplies_sql,
+        ))
+        .await?
+    {
+        let supply_ref: String = row.try_get("", "id").unwrap_or_default();
+        if let Ok(income_id) = row.try_get::<i64>("", "income_id") {
+            supply_by_income.insert(income_id, supply_ref.clone());
+        }
+        supplies.insert(
+            supply_ref.clone(),
+            SupplyHead {
+                supply_ref,
+                supply_id: row.try_get("", "supply_id").unwrap_or_default(),
+                supply_name: row.try_get("", "supply_name").ok(),
+                created_at_wb: row.try_get("", "created_at_wb").ok(),
+                connection_mp_ref: row.try_get("", "connection_id").unwrap_or_default(),
+            },
+        );
+    }
+
+    let mut accum = BTreeMap::<(String, Option<i64>), SupplyAcceptanceAccum>::new();
+
+    // Отгружено: строки заказов внутри поставки, по одной единице на строку.
+    let shipped_sql = format!(
+        "SELECT s.id AS supply_ref, \
+                CAST(json_extract(j.value, '$.nm_id') AS INTEGER) AS nm_id, \
+                COALESCE(MAX(json_extract(j.value, '$.article')), '') AS article, \
+                COUNT(*) AS shipped_qty \
+         FROM a029_wb_supply s, json_each(s.supply_orders_json) j \
+         WHERE {conditions} AND json_valid(s.supply_orders_json) \
+         GROUP BY s.id, CAST(json_extract(j.value, '$.nm_id') AS INTEGER)"
+    );
+    for row in db
+        .query_all(Statement::from_string(
+            sea_orm::DatabaseBackend::Sqlite,
+            shipped_sql,
+        ))
+        .await?
+    {
+        let supply_ref: String = row.try_get("", "supply_ref").unwrap_or_default();
+        let nm_id = row.try_get::<i64>("", "nm_id").ok();
+        let entry = accum.entry((supply_ref, nm_id)).or_default();
+        entry.article = row.try_get("", "article").unwrap_or_default();
+        entry.shipped_qty += row.try_get::<i64>("", "shipped_qty").unwrap_or(0);
+    }
+
+    // Принято/продано: заказы a015, которые WB относит к поставке по income_id.
+    // Продажа ищется без периода, поэтому и в архиве a012.
+    let a012 = crate::shared::data::projection_archive::source("a012_wb_sales", None);
+    let accepted_sql = format!(
+        "SELECT CAST(json_extract(w.source_meta_json, '$.income_id') AS INTEGER) AS income_id, \
+                CAST(json_extract(w.line_json, '$.nm_id') AS INTEGER) AS nm_id, \
+                COALESCE(MAX(json_extract(w.line_json, '$.supplier_article')), '') AS article, \
+                COUNT(*) AS accepted_qty, \
+                SUM(CASE WHEN EXISTS( \
+                        SELECT 1 FROM {a012} sl \
+                        WHERE sl.document_no = w.document_no \
+                          AND sl.is_deleted = 0 \
+                          AND sl.total_price > 0 \
+                    ) THEN 1 ELSE 0 END) AS sold_qty \
+         FROM a015_wb_orders w \
+         WHERE w.is_deleted = 0 \
+           AND CAST(json_extract(w.source_meta_json, '$.income_id') AS INTEGER) IN ( \
+               SELECT CAST(SUBSTR(s.supply_id, 7) AS INTEGER) \
+               FROM a029_wb_supply s \
+               WHERE {conditions}) \
+         GROUP BY 1, 2"
+    );
+    for row in db
+        .query_all(Statement::from_string(
+            sea_orm::DatabaseBackend::Sqlite,
+            accepted_sql,
+        ))
+        .await?
+    {
+        let Some(supply_ref) = row
+            .try_get::<i64>("", "income_id")
+            .ok()
+            .and_then(|income_id| supply_by_income.get(&income_id))
+        else {
+            continue;
+        };
+        let nm_id = row.try_get::<i64>("", "nm_id").ok();
+        let entry = accum.entry((supply_ref.clone(), nm_id)).or_default();
+        if entry.article.is_empty() {
+            entry.article = row.try_get("", "article").unwrap_or_default();
+        }
+        entry.accepted_qty += row.try_get::<i64>("", "accepted_qty").unwrap_or(0);
+        entry.sold_qty += row.try_get::<i64>("", "sold_qty").unwrap_or(0);
+    }
+
+    let mut totals = WbSupplyAcceptanceTotals {
+        supplies: supplies.len() as i64,
+        ..Default::default()
+    };
+    let mut rows = accum
+        .into_iter()
+        .filter_map(|((supply_ref, nm_id), item)| {
+            let head = supplies.get(&supply_ref)?;
+            let shortfall = supply_shortfall(item.shipped_qty, item.accepted_qty);
+            let is_flagged = is_flagged(shortfall, threshold);
+            totals.shipped_qty += item.shipped_qty;
+            totals.accepted_qty += item.accepted_qty;
+            totals.sold_qty += item.sold_qty;
+            totals.shortfall += shortfall;
+            totals.flagged_rows += i64::from(is_flagged);
+            Some(WbSupplyAcceptanceRow {
+                supply_ref: head.supply_ref.clone(),
+                supply_id: head.supply_id.clone(),
+                supply_name: head.supply_name.clone(),
+                created_at_wb: head.created_at_wb.clone(),
+                connection_mp_ref: head.connection_mp_ref.clone(),
+                nm_id,
+                article: item.article,
+                shipped_qty: item.shipped_qty,
+                accepted_qty: item.accepted_qty,
+                sold_qty: item.sold_qty,
+                shortfall,
+                is_flagged,
+            })
+        })
+        .collect::<Vec<_>>();
+    rows.sort_by(|a, b| {
+        b.created_at_wb
+            .cmp(&a.created_at_wb)
+            .then_with(|| a.supply_id.cmp(&b.supply_id))
+            .then_with(|| a.article.cmp(&b.article))
+    });
+
+    Ok(WbSupplyAcceptanceResponse {
+        filters,
+        threshold,
+        totals,
+        rows,
+    })
+}
+
+/// GET /api/dashboards/pnl-statement?date_from=..&date_to=..&organization_ref=..&layer=..
+/// Помесячный P&L по организациям (D408), собранный из проводок GL.
+pub async fn pnl_statement(
+    active_org: ActiveOrganization,
+    Query(mut filters): Query<PnlStatementRequest>,
+) -> Result<Json<PnlStatementResponse>, axum::http::StatusCode> {
+    filters.organization_ref = active_org.scope(filters.organization_ref.take());
+    build_pnl_statement(filters)
+        .await
+        .map(Json)
+        .map_err(|error| {
+            tracing::error!("pnl_statement failed: {}", error);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+#[derive(Default)]
+struct PnlOrganizationAccum {
+    organization_name: String,
+    connection_mp_refs: BTreeSet<String>,
+    /// (строка, оборот, слой) → значения по месяцам.
+    turnovers: BTreeMap<(PnlLine, String, String), Vec<f64>>,
+}
+
+async fn build_pnl_statement(filters: PnlStatementRequest) -> anyhow::Result<PnlStatementResponse> {
+    use crate::general_ledger::account_registry::is_profit_loss_account;
+    use crate::general_ledger::turnover_registry::get_turnover_class;
+    use sea_orm::{ConnectionTrait, Statement};
+
+    let db = crate::shared::data::db::get_connection();
+    let months = months_in_period(&filters.date_from, &filters.date_to);
+    let month_index: HashMap<&str, usize> = months
+        .iter()
+        .enumerate()
+        .map(|(index, month)| (month.as_str(), index))
+        .collect();
+    let explicit_layer = filters
+        .layer
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+
+    let layers: BTreeSet<&str> = PnlLine::ALL
+        .iter()
+        .map(|line| source_layer(*line, explicit_layer))
+        .collect();
+    let mut conditions = vec![
+        format!("gl.entry_date >= {}", sql_lit(&filters.date_from)),
+        format!("gl.entry_date <= {}", sql_lit(&filters.date_to)),
+        format!(
+            "gl.layer IN ({})",
+            layers
+                .iter()
+                .map(|layer| sql_lit(layer))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    ];
+    if let Some(value) = filters
+        .organization_ref
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        conditions.push(format!("c.organization_ref = {}", sql_lit(value)));
+    }
+
+    let sql = format!(
+        "SELECT SUBSTR(gl.entry_date, 1, 7) AS month, \
+                COALESCE(c.organization_ref, '') AS organization_ref, \
+                COALESCE(o.description, '') AS organization_name, \
+                gl.connection_mp_ref, gl.turnover_code, gl.layer, \
+                gl.debit_account, gl.credit_account, \
+                COALESCE(SUM(gl.amount), 0.0) AS amount \
+         FROM sys_general_ledger gl \
+         LEFT JOIN a006_connection_mp c ON c.id = gl.connection_mp_ref \
+         LEFT JOIN a002_organization o ON o.id = c.organization_ref \
+         WHERE {} \
+         GROUP BY month, organization_ref, gl.connection_mp_ref, gl.turnover_code, gl.layer, \
+                  gl.debit_account, gl.credit_account",
+        conditions.join(" AND ")
+    );
+
+    let mut organizations = BTreeMap::<String, PnlOrganizationAccum>::new();
+    for row in db
+        .query_all(Statement::from_string(
+            sea_orm::DatabaseBackend::Sqlite,
+            sql,
+        ))
+        .await?
+    {
+        let month: String = row.try_get("", "month").unwrap_or_default();
+        let Some(&index) = month_index.get(month.as_str()) else {
+            continue;
+        };
+        let turnover_code: String = row.try_get("", "turnover_code").unwrap_or_default();
+        let layer: String = row.try_get("", "layer").unwrap_or_default();
+        let debit_account: String = row.try_get("", "debit_account").unwrap_or_default();
+        let credit_account: String = row.try_get("", "credit_account").unwrap_or_default();
+        let amount: f64 = row.try_get("", "amount").unwrap_or(0.0);
+
+        let Some(class) = get_turnover_class(&turnover_code) else {
+            continue;
+        };
+        let line = PnlLine::from_report_group(class.report_group);
+        // Одна статья проводится в нескольких слоях — берём только её слой-источник.
+        if layer != source_layer(line, explicit_layer) {
+            continue;
+        }
+        let effect = pnl_effect(
+            amount,
+            is_profit_loss_account(&debit_account),
+            is_profit_loss_account(&credit_account),
+        );
+        if effect == 0.0 {
+            continue;
+        }
+
+        let organization_ref: String = row.try_get("", "organization_ref").unwrap_or_default();
+        let organization = organizations.entry(organization_ref).or_default();
+        if organization.organization_name.is_empty() {
+            organization.organization_name =
+                row.try_get("", "organization_name").unwrap_or_default();
+        }
+        if let Ok(connection_mp_ref) = row.try_get::<String>("", "connection_mp_ref") {
+            organization.connection_mp_refs.insert(connection_mp_ref);
+        }
+        organization
+            .turnovers
+            .entry((line, turnover_code, layer))
+            .or_insert_with(|| vec![0.0; months.len()])[index] += effect;
+    }
+
+    let sections = organizations
+        .into_iter()
+        .map(|(organization_ref, accum)| {
+            let mut lines = Vec::<PnlLineRow>::new();
+            for ((line, turnover_code, layer), values) in accum.turnovers {
+                if lines.last().map(|row| row.line) != Some(line) {
+                    lines.push(PnlLineRow {
+                        line,
+                        label: line.label().to_string(),
+                        values: vec![0.0; months.len()],
+                        total: 0.0,
+                        turnovers: Vec::new(),
+                    });
+                }
+                let line_row = lines.last_mut().expect("line row pushed above");
+                for (sum, value) in line_row.values.iter_mut().zip(&values) {
+                    *sum += value;
+                }
+                let total: f64 = values.iter().sum();
+                line_row.total += total;
+                line_row.turnovers.push(PnlTurnoverRow {
+                    turnover_name: get_turnover_class(&turnover_code)
+                        .map(|class| class.name.to_string())
+                        .unwrap_or_else(|| turnover_code.clone()),
+                    turnover_code,
+                    layer,
+                    values,
+                    total,
+                });
+            }
+            let mut result = vec![0.0; months.len()];
+            for line_row in &lines {
+                for (sum, value) in result.iter_mut().zip(&line_row.values) {
+                    *sum += value;
+                }
+            }
+            PnlOrganizationSection {
+                organization_name: if accum.organization_name.is_empty() {
+                    "Без организации".to_string()
+                } else {
+                    accum.organization_name
+                },
+                organization_ref,
+                connection_mp_refs: accum.connection_mp_refs.into_iter().collect(),
+                lines,
+                result_total: result.iter().sum(),
+                result,
+            }
+        })
+        .collect();
+
+    Ok(PnlStatementResponse {
+        filters,
+        months,
+        organizations: sections,
+    })
+}
+
+/// POST /api/dashboards/margin-scenario
+/// Сценарный расчёт маржи (D409): факт по SKU за период против допущений пользователя.
+pub async fn margin_scenario(
+    active_org: ActiveOrganization,
+    Json(request): Json<MarginScenarioRequest>,
+) -> Result<Json<MarginScenarioResponse>, axum::http::StatusCode> {
+    if request.items.is_empty() || request.items.len() > MAX_SCENARIO_ITEMS {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+    build_margin_scenario(request, active_org.0.as_deref())
+        .await
+        .map(Json)
+        .map_err(|error| {
+            tracing::error!("margin_scenario failed: {}", error);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+#[derive(Default)]
+struct MarginActualAccum {
+    article: String,
+    sold_qty: f64,
+    revenue: f64,
+    commission: f64,
+    acquiring: f64,
+    logistics: f64,
+    /// Хранение и приёмка, распределённые на SKU (p918), положительной суммой.
+    storage: f64,
+    order_price: f64,
+    cost: f64,
+    orders_margin_pro: Option<f64>,
+}
+
+impl MarginActualAccum {
+    /// Фактическая экономика единицы. Без продаж в отчёте WB цена берётся
+    /// средней по заказам, удержания считаются нулевыми.
+    fn unit_economics(&self) -> UnitEconomics {
+        let per_revenue = |value: f64| {
+            if self.revenue > 0.0 {
+                value / self.revenue * 100.0
+            } else {
+                0.0
+            }
+        };
+        let per_unit = |value: f64| {
+            if self.sold_qty > 0.0 {
+                value / self.sold_qty
+            } else {
+                0.0
+            }
+        };
+        UnitEconomics {
+            price: if self.sold_qty > 0.0 {
+                self.revenue / self.sold_qty
+            } else {
+                self.order_price
+            },
+            commission_percent: per_revenue(self.commission),
+            acquiring_percent: per_revenue(self.acquiring),
+            logistics_per_unit: per_unit(self.logistics),
+            cost_per_unit: self.cost,
+            storage_per_unit: per_unit(self.storage),
+        }
+    }
+}
+
+async fn build_margin_scenario(
+    request: MarginScenarioRequest,
+    organization_ref: Option<&str>,
+) -> anyhow::Result<MarginScenarioResponse> {
+    use sea_orm::{ConnectionTrait, Statement};
+
+    let db = crate::shared::data::db::get_connection();
+    let nm_ids = request
+        .items
+        .iter()
+        .map(|item| item.nm_id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let connection = request
+        .connection_mp_ref
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let mut actuals = HashMap::<i64, MarginActualAccum>::new();
+
+    // Цена и удержания — из финансового отчёта WB: продажи и строки логистики.
+    let mut finance_conditions = vec![
+        format!("nm_id IN ({nm_ids})"),
+        format!("rr_dt >= {}", sql_lit(&request.date_from)),
+        format!("rr_dt <= {}", sql_lit(&request.date_to)),
+    ];
+    if let Some(value) = connection {
+        finance_conditions.push(format!("connection_mp_ref = {}", sql_lit(value)));
+    }
+    if let Some(value) = organization_ref {
+        finance_conditions.push(organization_connection_condition("connection_mp_ref", value));
+    }
+    let finance_sql = format!(
+        "SELECT nm_id, \
+                COALESCE(MAX(sa_name), '') AS article, \
+                SUM(CASE WHEN supplier_oper_name = 'Продажа' THEN COALESCE(quantity, 0) ELSE 0 END) AS sold_qty, \
+                SUM(CASE WHEN supplier_oper_name = 'Продажа' THEN COALESCE(retail_amount, 0) ELSE 0 END) AS revenue, \
+                SUM(CASE WHEN supplier_oper_name = 'Продажа' \
+                         THEN COALESCE(ppvz_vw, 0) + COALESCE(ppvz_vw_nds, 0) ELSE 0 END) AS commission, \
+                SUM(CASE WHEN supplier_oper_name = 'Продажа' THEN COALESCE(acquiring_fee, 0) ELSE 0 END) AS acquiring, \
+                SUM(COALESCE(delivery_rub, 0)) AS logistics \
+         FROM p903_wb_finance_report \
+         WHERE {} \
+         GROUP BY nm_id",
+        finance_conditions.join(" AND ")
+    );
+    for row in db
+        .query_all(Statement::from_string(
+            sea_orm::DatabaseBackend::Sqlite,
+            finance_sql,
+        ))
+        .await?
+    {
+        let Ok(nm_id) = row.try_get::<i64>("", "nm_id") else {
+            continue;
+        };
+        let entry = actuals.entry(nm_id).or_default();
+        entry.article = row.try_get("", "article").unwrap_or_default();
+        entry.sold_qty = row.try_get::<f64>("", "sold_qty").unwrap_or(0.0);
+        entry.revenue = row.try_get::<f64>("", "revenue").unwrap_or(0.0);
+        entry.commission = row.try_get::<f64>("", "commission").unwrap_or(0.0);
+        entry.acquiring = row.try_get::<f64>("", "acquiring").unwrap_or(0.0);
+        entry.logistics = row.try_get::<f64>("", "logistics").unwrap_or(0.0);
+    }
+
+    // Себестоимость — дилерская цена заказов a015, та же, что в margin_pro.
+    let mut order_conditions = vec![
+        "w.is_deleted = 0".to_string(),
+        format!("CAST(json_extract(w.line_json, '$.nm_id') AS INTEGER) IN ({nm_ids})"),
+        format!(
+            "SUBSTR(w.document_date, 1, 10) >= {}",
+            sql_lit(&request.date_from)
+        ),
+        format!(
+            "SUBSTR(w.document_date, 1, 10) <= {}",
+            sql_lit(&request.date_to)
+        ),
+    ];
+    if let Some(value) = connection {
+        order_conditions.push(format!(
+            "json_extract(w.header_json, '$.connection_id') = {}",
+            sql_lit(value)
+        ));
+    }
+    if let Some(value) = organization_ref {
+        order_conditions.push(organization_connection_condition(
+            "json_extract(w.header_json, '$.connection_id')",
+            value,
+        ));
+    }
+    let orders_sql = format!(
+        "SELECT CAST(json_extract(w.line_json, '$.nm_id') AS INTEGER) AS nm_id, \
+                COALESCE(MAX(json_extract(w.line_json, '$.supplier_article')), '') AS article, \
+                AVG(COALESCE(NULLIF(CAST(json_extract(w.line_json, '$.sale_price') AS REAL), 0), \
+                             CAST(json_extract(w.line_json, '$.price_with_disc') AS REAL))) AS order_price, \
+                AVG(NULLIF(w.dealer_price_ut, 0)) AS cost, \
+                AVG(CAST(json_extract(w.line_json, '$.margin_pro') AS REAL)) AS margin_pro \
+         FROM a015_wb_orders w \
+         WHERE {} \
+         GROUP BY 1",
+        order_conditions.join(" AND ")
+    );
+    for row in db
+        .query_all(Statement::from_string(
+            sea_orm::DatabaseBackend::Sqlite,
+            orders_sql,
+        ))
+        .await?
+    {
+        let Ok(nm_id) = row.try_get::<i64>("", "nm_id") else {
+            continue;
+        };
+        let entry = actuals.entry(nm_id).or_default();
+        if entry.article.is_empty() {
+            entry.article = row.try_get("", "article").unwrap_or_default();
+        }
+        entry.order_price = row.try_get::<f64>("", "order_price").unwrap_or(0.0);
+        entry.cost = row.try_get::<f64>("", "cost").unwrap_or(0.0);
+        entry.orders_margin_pro = row.try_get::<Option<f64>>("", "margin_pro").ok().flatten();
+    }
+
+    // Хранение — доли расходов склада/поставки, распределённые на SKU (p918);
+    // в p918 расходы отрицательные.
+    let requested: Vec<i64> = request.items.iter().map(|item| item.nm_id).collect();
+    for (nm_id, amount) in crate::projections::p918_storage_cost_allocation::repository::amounts_by_nm(
+        &requested,
+        &request.date_from,
+        &request.date_to,
+        connection,
+        organization_ref,
+    )
+    .await?
+    {
+        actuals.entry(nm_id).or_default().storage = -amount;
+    }
+
+    let skus: Vec<String> = request
+        .items
+        .iter()
+        .map(|item| item.nm_id.to_string())
+        .collect();
+    let mut abc_xyz_labels =
+        crate::domain::a007_marketplace_product::abc_xyz::labels_for_skus(&skus).await?;
+
+    let mut totals = MarginScenarioTotals::default();
+    let rows = request
+        .items
+        .iter()
+        .map(|item| {
+            let accum = actuals.remove(&item.nm_id).unwrap_or_default();
+            let actual = accum.unit_economics();
+            let scenario = apply_assumptions(&actual, item.price, &request.assumptions);
+            let row = MarginScenarioRow {
+                nm_id: item.nm_id,
+                article: accum.article,
+                sold_qty: accum.sold_qty,
+                orders_margin_pro: accum.orders_margin_pro,
+                abc_xyz: abc_xyz_labels.remove(&item.nm_id.to_string()),
+                actual_profit_per_unit: actual.profit_per_unit(),
+                scenario_profit_per_unit: scenario.profit_per_unit(),
+                actual_markup_percent: actual.markup_percent(),
+                scenario_markup_percent: scenario.markup_percent(),
+                actual,
+                scenario,
+            };
+            totals.sold_qty += row.sold_qty;
+            totals.actual_profit += row.actual_profit_per_unit * row.sold_qty;
+            totals.scenario_profit += row.scenario_profit_per_unit * row.sold_qty;
+            row
+        })
+        .collect();
+
+    Ok(MarginScenarioResponse { rows, totals })
+}
+
+/// GET /api/dashboards/sku-launch-cohorts?date_from=..&date_to=..&connection_mp_ref=..
+/// Когорты запусков SKU (D410): накопленная выручка и маржа по месяцам жизни.
+pub async fn sku_launch_cohorts(
+    active_org: ActiveOrganization,
+    Query(filters): Query<SkuLaunchCohortsRequest>,
+) -> Result<Json<SkuLaunchCohortsResponse>, axum::http::StatusCode> {
+    if filters.date_from.len() < 7 || filters.date_to.len() < 7 {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+    build_sku_launch_cohorts(filters, active_org.0.as_deref())
+        .await
+        .map(Json)
+        .map_err(|error| {
+            tracing::error!("sku_launch_cohorts failed: {}", error);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+async fn build_sku_launch_cohorts(
+    filters: SkuLaunchCohortsRequest,
+    organization_ref: Option<&str>,
+) -> anyhow::Result<SkuLaunchCohortsResponse> {
+    use crate::projections::p900_mp_sales_register::repository as p900;
+
+    let horizon = filters.horizon();
+    let launch_from = &filters.date_from[..7];
+    let launch_to = &filters.date_to[..7];
+    let connection_mp_ref = filters
+        .connection_mp_ref
+        .as_deref()
+        .filter(|value| !value.is_empty());
+    let current_month = chrono::Utc::now().format("%Y-%m").to_string();
+
+    let sizes =
+        p900::launch_cohort_sizes(launch_from, launch_to, connection_mp_ref, organization_ref)
+            .await?;
+    let months =
+        p900::launch_cohort_months(launch_from, launch_to, connection_mp_ref, organization_ref)
+            .await?;
+    let mut by_cohort: HashMap<(String, u32), p900::LaunchCohortMonthRow> = HashMap::new();
+    for row in months {
+        if let Some(offset) = month_offset(&row.launch_month, &row.sale_month) {
+            by_cohort.insert((row.launch_month.clone(), offset), row);
+        }
+    }
+
+    let mut cohorts = Vec::with_capacity(sizes.len());
+    for (launch_month, sku_count) in sizes {
+        // Ячейки до горизонта, но не дальше текущего месяца: будущее — не нули.
+        let last_offset = month_offset(&launch_month, &current_month)
+            .unwrap_or(0)
+            .min(horizon - 1);
+        let mut cells: Vec<CohortMonthCell> = (0..=last_offset)
+            .map(|offset| {
+                let month = add_months(&launch_month, offset).unwrap_or_default();
+                match by_cohort.get(&(launch_month.clone(), offset)) {
+                    Some(row) => CohortMonthCell {
+                        offset,
+                        month,
+                        active_skus: row.active_skus,
+                        revenue: row.revenue,
+                        margin: row.margin,
+                        ..Default::default()
+                    },
+                    None => CohortMonthCell {
+                        offset,
+                        month,
+                        ..Default::default()
+                    },
+                }
+            })
+            .collect();
+        accumulate(&mut cells, sku_count);
+        let payback_offset = payback_offset(&cells, filters.launch_cost_per_sku);
+        cohorts.push(SkuLaunchCohort {
+            launch_month,
+            sku_count,
+            cells,
+            payback_offset,
+        });
+    }
+
+    Ok(SkuLaunchCohortsResponse {
+        filters,
+        horizon_months: horizon,
+        cohorts,
+    })
+}
+
+/// GET /api/dashboards/demand-forecast?date_from=..&date_to=..&connection_mp_ref=..&lead_weeks=..
+/// Прогноз спроса против факта (D412): прогноз с упреждением, метрики ошибки и последний расчёт.
+pub async fn demand_forecast(
+    active_org: ActiveOrganization,
+    Query(filters): Query<DemandForecastRequest>,
+) -> Result<Json<DemandForecastResponse>, axum::http::StatusCode> {
+    let parse = |value: &str| chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").ok();
+    let (Some(date_from), Some(date_to)) = (parse(&filters.date_from), parse(&filters.date_to))
+    else {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    };
+    if date_from > date_to {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+    build_demand_forecast(filters, date_from, date_to, active_org.0)
+        .await
+        .map(Json)
+        .map_err(|error| {
+            tracing::error!("demand_forecast failed: {}", error);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+async fn build_demand_forecast(
+    filters: DemandForecastRequest,
+    date_from: chrono::NaiveDate,
+    date_to: chrono::NaiveDate,
+    organization_ref: Option<String>,
+) -> anyhow::Result<DemandForecastResponse> {
+    use crate::domain::a007_marketplace_product::demand_forecast::{self, ForecastFilter};
+
+    let lead_weeks = filters.lead_weeks.unwrap_or(1).min(52);
+    let filter = ForecastFilter {
+        connection_mp_ref: filters
+            .connection_mp_ref
+            .clone()
+            .filter(|value| !value.is_empty()),
+        marketplace_product_ref: filters
+            .marketplace_product_ref
+            .clone()
+            .filter(|value| !value.is_empty()),
+        organization_ref,
+    };
+    let week_from = demand_forecast::week_start(date_from);
+    let week_to = demand_forecast::week_start(date_to) + chrono::Duration::weeks(1);
+    // Факт только по завершённым неделям: текущая неделя ещё не закрыта.
+    let current_week = demand_forecast::week_start(chrono::Utc::now().date_naive());
+    let format = |date: chrono::NaiveDate| date.format("%Y-%m-%d").to_string();
+
+    let actuals = demand_forecast::weekly_actuals(
+        &filter,
+        &format(week_from),
+        &format(week_to.min(current_week)),
+    )
+    .await?;
+    let forecasts = demand_forecast::forecasts_at_lead(
+        &filter,
+        &format(week_from),
+        &format(week_to),
+        lead_weeks,
+    )
+    .await?;
+    let latest = demand_forecast::latest_run(&filter).await?;
+
+    let mut actual_by_week: BTreeMap<String, f64> = BTreeMap::new();
+    let mut actual_by_product: HashMap<String, HashMap<String, f64>> = HashMap::new();
+    for (product_ref, week, qty) in actuals {
+        *actual_by_week.entry(week.clone()).or_default() += qty;
+        *actual_by_product
+            .entry(product_ref)
+            .or_default()
+            .entry(week)
+            .or_default() += qty;
+    }
+    let mut forecast_by_week: BTreeMap<String, f64> = BTreeMap::new();
+    let mut forecast_by_product: HashMap<String, Vec<(String, f64)>> = HashMap::new();
+    for (product_ref, week, qty) in forecasts {
+        *forecast_by_week.entry(week.clone()).or_default() += qty;
+        forecast_by_product
+            .entry(product_ref)
+            .or_default()
+            .push((week, qty));
+    }
+
+    let mut history = Vec::new();
+    let mut pairs = Vec::new();
+    let mut week = week_from;
+    while week < week_to {
+        let key = format(week);
+        let actual_qty =
+            (week < current_week).then(|| actual_by_week.get(&key).copied().unwrap_or_default());
+        let forecast_qty = forecast_by_week.get(&key).copied();
+        if let (Some(forecast), Some(actual)) = (forecast_qty, actual_qty) {
+            pairs.push((forecast, actual));
+        }
+        history.push(DemandForecastPoint {
+            week_start: key,
+            actual_qty,
+            forecast_qty,
+        });
+        week += chrono::Duration::weeks(1);
+    }
+
+    let current_week_key = format(current_week);
+    let mut outlook_by_week: BTreeMap<String, f64> = BTreeMap::new();
+    let mut next_weeks: HashMap<String, (f64, Option<ForecastMethod>)> = HashMap::new();
+    let latest_run = latest.map(|(made_on, rows)| {
+        let summary_end = chrono::NaiveDate::parse_from_str(&made_on, "%Y-%m-%d")
+            .map(|made_on| format(made_on + chrono::Duration::weeks(PRODUCT_SUMMARY_WEEKS as i64)))
+            .unwrap_or_default();
+        for (product_ref, week, qty, method) in rows {
+            if week >= current_week_key {
+                *outlook_by_week.entry(week.clone()).or_default() += qty;
+            }
+            let entry = next_weeks.entry(product_ref).or_insert((0.0, method));
+            if week < summary_end {
+                entry.0 += qty;
+            }
+        }
+        made_on
+    });
+    let outlook = outlook_by_week
+        .into_iter()
+        .map(|(week_start, qty)| DemandForecastPoint {
+            week_start,
+            actual_qty: None,
+            forecast_qty: Some(qty),
+        })
+        .collect();
+
+    let product_refs: Vec<String> = next_weeks
+        .keys()
+        .chain(actual_by_product.keys())
+        .chain(forecast_by_product.keys())
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let labels = demand_forecast::product_labels(&product_refs).await?;
+    let mut products: Vec<DemandForecastProductRow> = product_refs
+        .into_iter()
+        .map(|product_ref| {
+            let actual = actual_by_product.get(&product_ref);
+            let product_pairs: Vec<(f64, f64)> = forecast_by_product
+                .get(&product_ref)
+                .map(|points| {
+                    points
+                        .iter()
+                        .filter(|(week, _)| *week < current_week_key)
+                        .map(|(week, forecast)| {
+                            let qty = actual.and_then(|weeks| weeks.get(week)).copied();
+                            (*forecast, qty.unwrap_or_default())
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let (forecast_next_weeks, method) =
+                next_weeks.get(&product_ref).copied().unwrap_or((0.0, None));
+            let (article, product_name) = labels.get(&product_ref).cloned().unwrap_or_default();
+            DemandForecastProductRow {
+                article,
+                product_name,
+                method,
+                forecast_next_weeks,
+                actual_qty: actual.map(|weeks| weeks.values().sum()).unwrap_or_default(),
+                metrics: error_metrics(&product_pairs),
+                marketplace_product_ref: product_ref,
+            }
+        })
+        .collect();
+    products.sort_by(|a, b| {
+        b.forecast_next_weeks
+            .total_cmp(&a.forecast_next_weeks)
+            .then_with(|| b.actual_qty.total_cmp(&a.actual_qty))
+    });
+
+    Ok(DemandForecastResponse {
+        filters,
+        lead_weeks,
+        latest_run,
+        history,
+        outlook,
+        metrics: error_metrics(&pairs),
+        products,
+    })
+}
+
+/// GET /api/dashboards/import-coverage?date_from=..&date_to=..&connection_mp_ref=..&problems_only=..
+/// Покрытие импорта (D414): сводки маркетплейсов по дням против загруженных документов.
+pub async fn import_coverage(
+    active_org: ActiveOrganization,
+    Query(filters): Query<ImportCoverageRequest>,
+) -> Result<Json<ImportCoverageResponse>, axum::http::StatusCode> {
+    let parse = |value: &str| chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").ok();
+    let (Some(date_from), Some(date_to)) = (parse(&filters.date_from), parse(&filters.date_to))
+    else {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    };
+    if date_from > date_to {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+    crate::dashboards::d414_import_coverage::service::build_report(
+        filters,
+        date_from,
+        date_to,
+        active_org.0.as_deref(),
+    )
+    .await
+        .map(Json)
+        .map_err(|error| {
+            tracing::error!("import_coverage failed: {}", error);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// GET /api/dashboards/sku-pnl?date_from=..&date_to=..&connection_mp_ref=..
+/// P&L по SKU (D415): показатели p904 за период и предыдущий период той же длины.
+pub async fn sku_pnl(
+    active_org: ActiveOrganization,
+    Query(filters): Query<SkuPnlRequest>,
+) -> Result<Json<SkuPnlResponse>, axum::http::StatusCode> {
+    let parse = |value: &str| chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").ok();
+    let (Some(date_from), Some(date_to)) = (parse(&filters.date_from), parse(&filters.date_to))
+    else {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    };
+    if date_from > date_to || (date_to - date_from).num_days() > 366 {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+    }
+    crate::dashboards::d415_sku_pnl::service::build_report(
+        filters,
+        date_from,
+        date_to,
+        active_org.0.as_deref(),
+    )
+    .await
+        .map(Json)
+        .map_err(|error| {
+            tracing::error!("sku_pnl failed: {}", error);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
 /// GET /api/dashboards/wb-order-flow?srid={srid}
 pub async fn wb_order_flow(
+    active_org: ActiveOrganization,
     Query(query): Query<WbOrderFlowQuery>,
 ) -> Result<Json<WbOrderFlowResponse>, axum::http::StatusCode> {
     let srid = query.srid.trim().to_string();
@@ -585,6 +1668,13 @@ pub async fn wb_order_flow(
             tracing::error!("wb_order_flow a015 {}: {}", srid, e);
             axum::http::StatusCode::INTERNAL_SERVER_ERROR
         })?;
+    // Заказ другой организации — как несуществующий: ленту по нему не собираем.
+    if order_opt
+        .as_ref()
+        .is_some_and(|order| !active_org.allows(&order.header.organization_id))
+    {
+        return Err(axum::http::StatusCode::NOT_FOUND);
+    }
 
     let order_item = order_opt.as_ref().map(|o| OrderFlowItem {
         id: o.base.id.value().to_string(),
@@ -638,9 +1728,14 @@ pub async fn wb_order_flow(
     };
 
     // 3. a012: продажи
-    let sales_raw = crate::domain::a012_wb_sales::repository::search_by_document_no(&srid)
+    let mut sales_raw = crate::domain::a012_wb_sales::repository::search_by_document_no(&srid)
         .await
         .unwrap_or_default();
+    sales_raw.retain(|sale| active_org.allows(&sale.header.organization_id));
+    if order_opt.is_none() && sales_raw.is_empty() && active_org.0.is_some() {
+        // Без документов организации строки отчётов по srid не показываем.
+        return Err(axum::http::StatusCode::NOT_FOUND);
+    }
 
     let sales: Vec<SaleFlowItem> = sales_raw
         .into_iter()
@@ -822,6 +1917,7 @@ pub async fn wb_order_flow(
 /// «Вся история» YM-заказа: строки реализации a034 + платёжные транзакции p907,
 /// собранные по номеру заказа.
 pub async fn ym_order_flow(
+    active_org: ActiveOrganization,
     Query(query): Query<YmOrderFlowQuery>,
 ) -> Result<Json<YmOrderFlowResponse>, axum::http::StatusCode> {
     let order_no = query.order_id.trim().to_string();
@@ -829,27 +1925,36 @@ pub async fn ym_order_flow(
     // 0. a013: сам заказ (первое событие ленты, как в d402).
     let order = crate::domain::a013_ym_order::repository::get_by_document_no(&order_no)
         .await
-        .unwrap_or(None)
-        .map(|o| {
-            let qty: f64 = o.lines.iter().map(|l| l.qty).sum();
-            YmOrderFlowItem {
-                id: o.base.id.0.to_string(),
-                document_no: o.header.document_no.clone(),
-                order_date: o
-                    .state
-                    .creation_date
-                    .map(|d| d.format("%d.%m.%Y").to_string()),
-                status: Some(o.state.status_norm.clone()).filter(|s| !s.trim().is_empty()),
-                delivery_date: o
-                    .state
-                    .delivery_date
-                    .map(|d| d.format("%d.%m.%Y").to_string()),
-                qty,
-                items_total: o.header.items_total,
-                total_amount: o.header.total_amount,
-                is_posted: o.is_posted,
-            }
-        });
+        .unwrap_or(None);
+    // Заказ другой организации — как несуществующий; без заказа ленту организации
+    // не собрать, строки реализации и платежи тоже не показываем.
+    match &order {
+        Some(o) if !active_org.allows(&o.header.organization_id) => {
+            return Err(axum::http::StatusCode::NOT_FOUND);
+        }
+        None if active_org.0.is_some() => return Err(axum::http::StatusCode::NOT_FOUND),
+        _ => {}
+    }
+    let order = order.map(|o| {
+        let qty: f64 = o.lines.iter().map(|l| l.qty).sum();
+        YmOrderFlowItem {
+            id: o.base.id.0.to_string(),
+            document_no: o.header.document_no.clone(),
+            order_date: o
+                .state
+                .creation_date
+                .map(|d| d.format("%d.%m.%Y").to_string()),
+            status: Some(o.state.status_norm.clone()).filter(|s| !s.trim().is_empty()),
+            delivery_date: o
+                .state
+                .delivery_date
+                .map(|d| d.format("%d.%m.%Y").to_string()),
+            qty,
+            items_total: o.header.items_total,
+            total_amount: o.header.total_amount,
+            is_posted: o.is_posted,
+        }
+    });
 
     // 1. a034: строки официальной реализации по заказу.
     let realizations: Vec<YmRealizationFlowItem> =
@@ -996,6 +2101,7 @@ async fn resolve_nomenclature_descriptions(
 /// Воронка продаж WB (d406) — агрегат p916 `товар × дата` по выбранной оси
 /// (когорта/событие) с именами товаров (джойн a004) и производными конверсиями.
 pub async fn wb_sales_funnel(
+    active_org: ActiveOrganization,
     Query(filters): Query<WbSalesFunnelRequest>,
 ) -> Result<Json<WbSalesFunnelResponse>, axum::http::StatusCode> {
     let request = MpFunnelListRequest {
@@ -1017,6 +2123,46 @@ pub async fn wb_sales_funnel(
             tracing::error!("wb_sales_funnel p916 aggregation failed: {}", error);
             axum::http::StatusCode::INTERNAL_SERVER_ERROR
         })?;
+    let agg_rows: Vec<_> = match organization_connections(&active_org).await? {
+        Some(visible) => agg_rows
+            .into_iter()
+            .filter(|row| visible.contains(&row.connection_mp_ref))
+            .collect(),
+        None => agg_rows,
+    };
+
+    // ABC/XYZ-классы товаров a007: метка строки и фильтр по классам.
+    let product_refs: Vec<String> = agg_rows
+        .iter()
+        .filter_map(|row| row.marketplace_product_ref.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let abc_xyz_labels =
+        crate::domain::a007_marketplace_product::abc_xyz::labels_for_products(&product_refs)
+            .await
+            .unwrap_or_else(|error| {
+                tracing::warn!("wb_sales_funnel ABC/XYZ lookup failed: {}", error);
+                HashMap::new()
+            });
+    let abc_filter = filters.abc_class.as_deref().and_then(AbcClass::from_code);
+    let xyz_filter = filters.xyz_class.as_deref().and_then(XyzClass::from_code);
+    let label_of = |product_ref: &Option<String>| {
+        product_ref
+            .as_ref()
+            .and_then(|product_ref| abc_xyz_labels.get(product_ref))
+    };
+    let agg_rows: Vec<_> = if abc_filter.is_none() && xyz_filter.is_none() {
+        agg_rows
+    } else {
+        agg_rows
+            .into_iter()
+            .filter(|row| {
+                label_of(&row.marketplace_product_ref)
+                    .is_some_and(|label| label.matches(abc_filter, xyz_filter))
+            })
+            .collect()
+    };
 
     // Имена товаров: джойн a004 по nomenclature_ref (одним запросом на весь набор).
     // Артикул и наименование храним раздельно — d406 показывает только артикул (наим. в тултипе).
@@ -1100,6 +2246,7 @@ pub async fn wb_sales_funnel(
                 metrics.cancel_count,
             );
 
+            let abc_xyz = label_of(&row.marketplace_product_ref).cloned();
             WbSalesFunnelRow {
                 date: row.date,
                 connection_mp_ref: row.connection_mp_ref,
@@ -1111,6 +2258,7 @@ pub async fn wb_sales_funnel(
                 article,
                 product_name,
                 brand: None,
+                abc_xyz,
                 metrics,
                 conversions,
             }
@@ -1182,8 +2330,14 @@ pub struct WbSalesFunnelOrdersQuery {
 /// «Платный» ⇔ srid заказа входит в атрибуцию рекламы p913 (`advert_clicks_order_accrual`).
 /// Счётчики `paid_count`/`free_count` считаются по всей ячейке (до фильтра `channel`).
 pub async fn wb_sales_funnel_orders(
+    active_org: ActiveOrganization,
     Query(query): Query<WbSalesFunnelOrdersQuery>,
 ) -> Result<Json<WbSalesFunnelOrdersResponse>, axum::http::StatusCode> {
+    if let Some(visible) = organization_connections(&active_org).await? {
+        if !visible.contains(&query.connection_mp_ref) {
+            return Err(axum::http::StatusCode::NOT_FOUND);
+        }
+    }
     let channel = match query.channel.as_deref() {
         Some("paid") => FunnelOrderChannel::Paid,
         Some("free") => FunnelOrderChannel::Free,
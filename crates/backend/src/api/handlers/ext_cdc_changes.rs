@@ -0,0 +1,97 @@
+//! External API: лента изменений (CDC) для инкрементальной синхронизации
+//! корпоративного DWH. Потребитель хранит последний полученный `offset` и
+//! запрашивает события после него; данные сущностей дочитывает обычными выгрузками.
+//! Authentication is handled by the `check_api_key` middleware (X-Api-Key header).
+//! The feed is not scoped to an active organization: the key is server-wide and
+//! belongs to the DWH, not to a user, and events carry only ids and operations.
+
+use axum::{extract::Query, Json};
+use serde::{Deserialize, Serialize};
+
+use crate::system::cdc::service;
+
+fn default_limit() -> u64 {
+    1_000
+}
+
+// ─────────────────────────────────────────────
+// Query params
+// ─────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct ChangesQuery {
+    /// Последний обработанный offset; `0` — с начала хранимой ленты.
+    #[serde(default)]
+    pub after: i64,
+    #[serde(default = "default_limit")]
+    pub limit: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChangeItem {
+    pub offset: i64,
+    /// UTC ISO8601.
+    pub ts: String,
+    /// `aggregate` | `projection`.
+    pub entity_kind: String,
+    /// `a012_wb_sales`, `a015_wb_orders`, `p900_mp_sales_register`, `p904_sales_data`.
+    pub entity_type: String,
+    /// Id агрегата; для проекций — registrator_ref (id документа-регистратора).
+    pub entity_id: String,
+    /// `created` | `updated` | `posted` | `unposted` | `deleted`.
+    pub op: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChangesResponse {
+    pub items: Vec<ChangeItem>,
+    /// Передать как `after` в следующий запрос.
+    pub next_offset: i64,
+    /// `true` — за `next_offset` есть ещё события, запрашивать сразу.
+    pub has_more: bool,
+    /// Наименьший хранимый offset. Если `after + 1 < min_offset`, часть событий уже
+    /// вычищена ретеншном — нужна полная перевыгрузка.
+    pub min_offset: Option<i64>,
+}
+
+// ─────────────────────────────────────────────
+// Handler
+// ─────────────────────────────────────────────
+
+/// События после `after` в порядке offset.
+///
+/// GET /api/ext/v1/changes
+///   ?after=0          (опц., по умолчанию 0)
+///   &limit=1000       (опц., потолок 10000)
+///
+/// Заголовок: `X-Api-Key: <ключ>`.
+pub async fn list_changes(
+    Query(q): Query<ChangesQuery>,
+) -> Result<Json<ChangesResponse>, axum::http::StatusCode> {
+    let page = service::list_changes(q.after.max(0), q.limit)
+        .await
+        .map_err(|e| {
+            tracing::error!("[ext-api] changes error: {}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let items = page
+        .items
+        .into_iter()
+        .map(|row| ChangeItem {
+            offset: row.change_offset,
+            ts: row.ts,
+            entity_kind: row.entity_kind,
+            entity_type: row.entity_type,
+            entity_id: row.entity_id,
+            op: row.op,
+        })
+        .collect();
+
+    Ok(Json(ChangesResponse {
+        items,
+        next_offset: page.next_offset,
+        has_more: page.has_more,
+        min_offset: page.min_offset,
+    }))
+}
@@ -57,6 +57,8 @@ pub mod p912_nomenclature_costs;
 pub mod p913_wb_advert_order_attr;
 pub mod p914_mp_finance_turnovers;
 pub mod p915_mp_order_events;
+pub mod p918_storage_cost_allocation;
+pub mod p919_daily_sales_summary;
 
 // DataView semantic layer handlers
 pub mod bi_timeline;
@@ -88,6 +90,8 @@ pub mod ext_bi_wb_funnel;
 pub mod ext_bi_wb_stocks;
 // External BI API (Power BI) — WB finance report p903 (raw native rows)
 pub mod ext_bi_wb_finance;
+// External CDC feed for the corporate DWH (sys_change_log)
+pub mod ext_cdc_changes;
 
 // Data quality checks
 pub mod quality;
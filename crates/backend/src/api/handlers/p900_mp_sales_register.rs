@@ -1,16 +1,23 @@
-use axum::{extract::Query, Json};
+use anyhow::Context;
+use axum::{
+    extract::{Path, Query},
+    Json,
+};
 use contracts::domain::common::AggregateId;
 use contracts::projections::p900_mp_sales_register::{
     SalesRegisterDetailDto, SalesRegisterDto, SalesRegisterListRequest, SalesRegisterListResponse,
     SalesRegisterStatsByDateRequest, SalesRegisterStatsByDateResponse,
-    SalesRegisterStatsByMarketplaceResponse,
+    SalesRegisterStatsByMarketplaceResponse, SkuSparklineRequest, SkuSparklineResponse,
 };
+use contracts::projections::source_document::source_aggregate;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use crate::projections::p900_mp_sales_register::{backfill, repository, service};
+use crate::shared::error::AppError;
+use crate::system::org_context::ActiveOrganization;
 
 // Cache для организаций
 static ORG_CACHE: Lazy<Arc<RwLock<Option<(std::time::Instant, HashMap<String, String>)>>>> =
@@ -51,24 +58,26 @@ async fn get_org_map() -> HashMap<String, String> {
 
 /// Handler для получения списка продаж с фильтрами
 pub async fn list_sales(
+    active_org: ActiveOrganization,
     Query(req): Query<SalesRegisterListRequest>,
-) -> Result<Json<SalesRegisterListResponse>, axum::http::StatusCode> {
+) -> Result<Json<SalesRegisterListResponse>, AppError> {
     let (items, total) = service::list_with_filters(
         &req.date_from,
         &req.date_to,
         req.marketplace,
-        req.organization_ref,
+        active_org.scope(req.organization_ref),
         req.connection_mp_ref,
         req.status_norm,
         req.seller_sku,
+        req.link_status,
+        req.warehouse,
+        req.sort_by.as_deref(),
+        req.sort_desc.unwrap_or(true),
         req.limit,
         req.offset,
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to list sales: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list sales")?;
 
     // Получаем кэш организаций
     let org_map = get_org_map().await;
@@ -89,19 +98,18 @@ pub async fn list_sales(
 
 /// Handler для получения детальной информации о продаже
 pub async fn get_sale_detail(
+    active_org: ActiveOrganization,
     axum::extract::Path((marketplace, document_no, line_id)): axum::extract::Path<(
         String,
         String,
         String,
     )>,
-) -> Result<Json<SalesRegisterDetailDto>, axum::http::StatusCode> {
+) -> Result<Json<SalesRegisterDetailDto>, AppError> {
     let item = service::get_by_id(&marketplace, &document_no, &line_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get sale detail: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+        .context("Failed to get sale detail")?
+        .filter(|item| active_org.allows(&item.organization_ref))
+        .ok_or_else(|| AppError::not_found("Запись не найдена"))?;
 
     // Получаем кэш организаций
     let org_map = get_org_map().await;
@@ -119,28 +127,44 @@ pub async fn get_sale_detail(
 
 /// Handler для статистики по датам
 pub async fn get_stats_by_date(
+    active_org: ActiveOrganization,
     Query(req): Query<SalesRegisterStatsByDateRequest>,
-) -> Result<Json<SalesRegisterStatsByDateResponse>, axum::http::StatusCode> {
-    let stats = service::calculate_daily_stats(&req.date_from, &req.date_to, req.marketplace)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to get stats by date: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+) -> Result<Json<SalesRegisterStatsByDateResponse>, AppError> {
+    let stats =
+        service::calculate_daily_stats(&req.date_from, &req.date_to, req.marketplace, &active_org)
+            .await
+            .context("Failed to get stats by date")?;
 
     Ok(Json(SalesRegisterStatsByDateResponse { data: stats }))
 }
 
+/// Handler для sparkline продаж товара МП (a007) в карточках документов
+pub async fn get_sku_sparkline(
+    Path(marketplace_product_ref): Path<String>,
+    Query(req): Query<SkuSparklineRequest>,
+) -> Result<Json<SkuSparklineResponse>, AppError> {
+    let date_to = match req.date_to.as_deref() {
+        Some(raw) => chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+            .map_err(|_| AppError::bad_request("Некорректная дата"))?,
+        None => chrono::Utc::now().date_naive(),
+    };
+    let days = req.days.unwrap_or(service::SPARKLINE_DEFAULT_DAYS);
+
+    let sparkline = service::sku_sparkline(&marketplace_product_ref, date_to, days)
+        .await
+        .context("Failed to get SKU sparkline")?;
+
+    Ok(Json(sparkline))
+}
+
 /// Handler для статистики по маркетплейсам
 pub async fn get_stats_by_marketplace(
+    active_org: ActiveOrganization,
     Query(req): Query<SalesRegisterStatsByDateRequest>,
-) -> Result<Json<SalesRegisterStatsByMarketplaceResponse>, axum::http::StatusCode> {
-    let stats = service::calculate_marketplace_stats(&req.date_from, &req.date_to)
+) -> Result<Json<SalesRegisterStatsByMarketplaceResponse>, AppError> {
+    let stats = service::calculate_marketplace_stats(&req.date_from, &req.date_to, &active_org)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get stats by marketplace: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to get stats by marketplace")?;
 
     Ok(Json(SalesRegisterStatsByMarketplaceResponse {
         data: stats,
@@ -148,15 +172,12 @@ pub async fn get_stats_by_marketplace(
 }
 
 /// Handler для запуска backfill marketplace_product_ref
-pub async fn backfill_product_refs() -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+pub async fn backfill_product_refs() -> Result<Json<serde_json::Value>, AppError> {
     tracing::info!("Starting backfill of marketplace_product_ref");
 
     let stats = backfill::backfill_marketplace_product_refs()
         .await
-        .map_err(|e| {
-            tracing::error!("Backfill failed: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Backfill failed")?;
 
     tracing::info!(
         "Backfill completed: total={}, updated={}, skipped={}, failed={}",
@@ -180,6 +201,7 @@ fn model_to_dto(model: repository::Model, org_map: &HashMap<String, String>) ->
     let organization_name = org_map.get(&model.organization_ref).cloned();
 
     SalesRegisterDto {
+        source_type: source_aggregate(&model.document_type).map(str::to_string),
         marketplace: model.marketplace,
         document_no: model.document_no,
         line_id: model.line_id,
@@ -213,19 +235,19 @@ fn model_to_dto(model: repository::Model, org_map: &HashMap<String, String>) ->
         loaded_at_utc: model.loaded_at_utc,
         payload_version: model.payload_version,
         extra: model.extra,
+        warehouse_name: model.warehouse_name,
     }
 }
 
 /// Handler для получения проекций по registrator_ref
 pub async fn get_by_registrator(
+    active_org: ActiveOrganization,
     axum::extract::Path(registrator_ref): axum::extract::Path<String>,
-) -> Result<Json<Vec<SalesRegisterDto>>, axum::http::StatusCode> {
-    let items = service::get_by_registrator(&registrator_ref)
+) -> Result<Json<Vec<SalesRegisterDto>>, AppError> {
+    let mut items = service::get_by_registrator(&registrator_ref)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get projections by registrator: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to get projections by registrator")?;
+    items.retain(|item| active_org.allows(&item.organization_ref));
 
     // Получаем кэш организаций
     let org_map = get_org_map().await;
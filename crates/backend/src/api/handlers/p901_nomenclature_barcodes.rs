@@ -1,4 +1,4 @@
-use axum::http::StatusCode;
+use anyhow::Context;
 use axum::{
     extract::{Path, Query},
     Json,
@@ -8,31 +8,30 @@ use contracts::projections::p901_nomenclature_barcodes::{
 };
 
 use crate::projections::p901_nomenclature_barcodes::{repository, service};
+use crate::shared::error::AppError;
 
 /// Handler для получения номенклатуры по штрихкоду и источнику
 /// Требует обязательный query параметр source (1C, OZON, WB, YM)
 pub async fn get_by_barcode(
     Path(barcode): Path<String>,
     Query(req): Query<contracts::projections::p901_nomenclature_barcodes::BarcodeByIdRequest>,
-) -> Result<Json<BarcodeByIdResponse>, StatusCode> {
+) -> Result<Json<BarcodeByIdResponse>, AppError> {
     // source - обязательный параметр
     if req.source.is_empty() {
-        tracing::error!("Source parameter is required for barcode lookup");
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(AppError::bad_request(
+            "Не указан источник штрихкода (source)",
+        ));
     }
 
     let model = repository::get_by_barcode_and_source(&barcode, &req.source)
         .await
-        .map_err(|e| {
-            tracing::error!(
-                "Failed to get barcode {} with source {}: {}",
-                barcode,
-                req.source,
-                e
-            );
-            StatusCode::INTERNAL_SERVER_ERROR
+        .with_context(|| {
+            format!(
+                "Failed to get barcode {} with source {}",
+                barcode, req.source
+            )
         })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .ok_or_else(|| AppError::not_found("Запись не найдена"))?;
 
     let dto = service::model_to_dto(&model);
 
@@ -45,16 +44,14 @@ pub async fn get_barcodes_by_nomenclature(
     Query(req): Query<
         contracts::projections::p901_nomenclature_barcodes::BarcodesByNomenclatureRequest,
     >,
-) -> Result<Json<BarcodesByNomenclatureResponse>, StatusCode> {
+) -> Result<Json<BarcodesByNomenclatureResponse>, AppError> {
     let models = repository::get_by_nomenclature_ref(&nomenclature_ref, req.include_inactive)
         .await
-        .map_err(|e| {
-            tracing::error!(
-                "Failed to get barcodes for nomenclature {}: {}",
-                nomenclature_ref,
-                e
-            );
-            StatusCode::INTERNAL_SERVER_ERROR
+        .with_context(|| {
+            format!(
+                "Failed to get barcodes for nomenclature {}",
+                nomenclature_ref
+            )
         })?;
 
     let dtos = service::models_to_dtos(models);
@@ -70,8 +67,9 @@ pub async fn get_barcodes_by_nomenclature(
 /// Handler для получения списка штрихкодов с фильтрами
 pub async fn list_barcodes(
     Query(req): Query<BarcodeListRequest>,
-) -> Result<Json<BarcodeListResponse>, StatusCode> {
+) -> Result<Json<BarcodeListResponse>, AppError> {
     let (models, total_count) = repository::list_with_filters(
+        req.barcode,
         req.nomenclature_ref,
         req.article,
         req.source,
@@ -80,10 +78,7 @@ pub async fn list_barcodes(
         req.offset,
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to list barcodes: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list barcodes")?;
 
     let dtos = service::barcodes_with_nomenclature_to_dtos(models);
 
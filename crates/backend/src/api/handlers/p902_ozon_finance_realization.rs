@@ -1,3 +1,4 @@
+use anyhow::Context;
 use axum::{extract::Query, Json};
 use contracts::projections::p902_ozon_finance_realization::dto::{
     OzonFinanceRealizationByIdResponse, OzonFinanceRealizationDto,
@@ -6,11 +7,12 @@ use contracts::projections::p902_ozon_finance_realization::dto::{
 };
 
 use crate::projections::p902_ozon_finance_realization::repository;
+use crate::shared::error::AppError;
 
 /// Handler для получения списка финансовых данных с фильтрами
 pub async fn list_finance_realization(
     Query(req): Query<OzonFinanceRealizationListRequest>,
-) -> Result<Json<OzonFinanceRealizationListResponse>, axum::http::StatusCode> {
+) -> Result<Json<OzonFinanceRealizationListResponse>, AppError> {
     let (items, total) = repository::list_with_filters(
         &req.date_from,
         &req.date_to,
@@ -27,10 +29,7 @@ pub async fn list_finance_realization(
         req.offset,
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to list finance realization: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list finance realization")?;
 
     let dtos: Vec<OzonFinanceRealizationDto> = items.into_iter().map(model_to_dto).collect();
 
@@ -50,14 +49,11 @@ pub async fn get_finance_realization_detail(
         String,
         String,
     )>,
-) -> Result<Json<OzonFinanceRealizationByIdResponse>, axum::http::StatusCode> {
+) -> Result<Json<OzonFinanceRealizationByIdResponse>, AppError> {
     let item = repository::get_by_id(&posting_number, &sku, &operation_type)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get finance realization detail: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+        .context("Failed to get finance realization detail")?
+        .ok_or_else(|| AppError::not_found("Запись не найдена"))?;
 
     Ok(Json(OzonFinanceRealizationByIdResponse {
         item: model_to_dto_simple(item),
@@ -67,13 +63,10 @@ pub async fn get_finance_realization_detail(
 /// Handler для получения статистики по периоду
 pub async fn get_stats(
     Query(req): Query<OzonFinanceRealizationStatsRequest>,
-) -> Result<Json<OzonFinanceRealizationStatsResponse>, axum::http::StatusCode> {
+) -> Result<Json<OzonFinanceRealizationStatsResponse>, AppError> {
     let stats = repository::get_stats(&req.date_from, &req.date_to, req.connection_mp_ref)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get finance realization stats: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to get finance realization stats")?;
 
     Ok(Json(OzonFinanceRealizationStatsResponse {
         total_rows: stats.total_rows,
@@ -1,31 +1,33 @@
-use axum::{
-    body::Body,
-    extract::Query,
-    http::{header, StatusCode},
-    response::Response,
-    Json,
-};
+use anyhow::Context;
+use axum::{extract::Query, Extension, Json};
 use chrono::NaiveDate;
 use contracts::general_ledger::GeneralLedgerEntryDto;
 use contracts::projections::p903_wb_finance_report::dto::{
     WbFinanceReportDetailResponse, WbFinanceReportDto, WbFinanceReportListRequest,
-    WbFinanceReportListResponse,
+    WbFinanceReportListResponse, WbFinanceReportSridTotals,
 };
+use contracts::system::exports::{ExportFileFormat, ExportJobDto, EXPORT_KIND_P903_FINANCE_REPORT};
 use serde::Deserialize;
 
 use crate::projections::p903_wb_finance_report::repository;
+use crate::shared::error::AppError;
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::exports;
+use crate::system::org_context::ActiveOrganization;
+use crate::system::quotas::service::{CurrentOrganization, QuotaExceeded};
 
 /// Handler для получения списка финансовых отчетов с фильтрами
 pub async fn list_reports(
+    active_org: ActiveOrganization,
     Query(req): Query<WbFinanceReportListRequest>,
-) -> Result<Json<WbFinanceReportListResponse>, axum::http::StatusCode> {
+) -> Result<Json<WbFinanceReportListResponse>, AppError> {
     let (items, total) = repository::list_with_filters(
         &req.date_from,
         &req.date_to,
         req.nm_id,
         req.sa_name,
         req.connection_mp_ref,
-        req.organization_ref,
+        active_org.scope(req.organization_ref),
         req.supplier_oper_name,
         req.srid,
         &req.sort_by,
@@ -34,19 +36,13 @@ pub async fn list_reports(
         req.offset,
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to list finance report: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list finance report")?;
 
     let gl_counts = crate::general_ledger::repository::count_by_registrator_refs(
         &items.iter().map(|item| item.id.clone()).collect::<Vec<_>>(),
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to count p903 general ledger rows: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to count p903 general ledger rows")?;
 
     let dtos: Vec<WbFinanceReportDto> = items
         .into_iter()
@@ -65,169 +61,78 @@ pub async fn list_reports(
     }))
 }
 
-/// Экспорт в CSV: все строки с учётом фильтров (без пагинации), все колонки
-/// таблицы `p903_wb_finance_report` с их оригинальными названиями.
-pub async fn export_reports(Query(req): Query<WbFinanceReportListRequest>) -> Response {
-    let items = match repository::list_all_with_filters(
-        &req.date_from,
-        &req.date_to,
-        req.nm_id,
-        req.sa_name,
-        req.connection_mp_ref,
-        req.organization_ref,
-        req.supplier_oper_name,
-        req.srid,
-        &req.sort_by,
-        req.sort_desc,
+/// Экспорт в CSV: фоновая выгрузка по фильтрам списка. Файл появится в «Мои выгрузки»,
+/// пользователь получит уведомление, когда он будет готов.
+pub async fn export_reports(
+    CurrentUser(claims): CurrentUser,
+    organization: Option<Extension<CurrentOrganization>>,
+    active_org: ActiveOrganization,
+    Json(mut req): Json<WbFinanceReportListRequest>,
+) -> Result<Json<ExportJobDto>, (axum::http::StatusCode, String)> {
+    req.organization_ref = active_org.scope(req.organization_ref.take());
+    let title = format!(
+        "WB Finance Report (P903) {} — {}",
+        req.date_from, req.date_to
+    );
+    exports::service::start(
+        EXPORT_KIND_P903_FINANCE_REPORT,
+        title,
+        "wb_finance_report".to_string(),
+        ExportFileFormat::Csv,
+        &claims.sub,
+        organization.map(|Extension(CurrentOrganization(id))| id),
+        async move { crate::projections::p903_wb_finance_report::export::build_csv(&req).await },
     )
     .await
-    {
-        Ok(items) => items,
-        Err(e) => {
-            tracing::error!("Failed to export finance report: {}", e);
-            return csv_plain_error(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Не удалось загрузить данные для экспорта",
-            );
-        }
-    };
-
-    match build_finance_report_csv(&items) {
-        Ok(buffer) => {
-            let filename = format!(
-                "wb_finance_report_{}.csv",
-                chrono::Utc::now().format("%Y%m%d_%H%M%S")
+    .map(Json)
+    .map_err(|e| {
+        if let Some(exceeded) = e.downcast_ref::<QuotaExceeded>() {
+            return (
+                axum::http::StatusCode::TOO_MANY_REQUESTS,
+                exceeded.to_string(),
             );
-            Response::builder()
-                .status(StatusCode::OK)
-                .header(header::CONTENT_TYPE, "text/csv; charset=utf-8")
-                .header(
-                    header::CONTENT_DISPOSITION,
-                    format!(r#"attachment; filename="{filename}""#),
-                )
-                .body(Body::from(buffer))
-                .unwrap_or_else(|_| Response::new(Body::empty()))
-        }
-        Err(e) => {
-            tracing::error!("Failed to build p903 export CSV: {}", e);
-            csv_plain_error(StatusCode::INTERNAL_SERVER_ERROR, "Ошибка формирования CSV")
         }
-    }
-}
-
-fn csv_plain_error(status: StatusCode, message: impl Into<String>) -> Response {
-    Response::builder()
-        .status(status)
-        .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
-        .body(Body::from(message.into()))
-        .unwrap_or_else(|_| Response::new(Body::empty()))
-}
-
-/// Заголовки = оригинальные имена колонок таблицы `p903_wb_finance_report`,
-/// в порядке объявления в `repository::Model`.
-const EXPORT_HEADERS: &[&str] = &[
-    "id",
-    "rr_dt",
-    "rrd_id",
-    "source_row_ref",
-    "connection_mp_ref",
-    "organization_ref",
-    "acquiring_fee",
-    "acquiring_percent",
-    "additional_payment",
-    "bonus_type_name",
-    "commission_percent",
-    "delivery_amount",
-    "delivery_rub",
-    "nm_id",
-    "a004_nomenclature_ref",
-    "penalty",
-    "ppvz_vw",
-    "ppvz_vw_nds",
-    "ppvz_sales_commission",
-    "quantity",
-    "rebill_logistic_cost",
-    "retail_amount",
-    "retail_price",
-    "retail_price_withdisc_rub",
-    "return_amount",
-    "sa_name",
-    "storage_fee",
-    "subject_name",
-    "supplier_oper_name",
-    "cashback_amount",
-    "ppvz_for_pay",
-    "ppvz_kvw_prc",
-    "ppvz_kvw_prc_base",
-    "srv_dbs",
-    "srid",
-    "loaded_at_utc",
-    "payload_version",
-];
-
-/// Десятичный разделитель — запятая (Excel/1С, ru-локаль). Полная точность.
-fn fmt_opt_f64(v: Option<f64>) -> String {
-    v.map(|x| x.to_string().replace('.', ","))
-        .unwrap_or_default()
+        tracing::error!("Failed to start finance report export: {}", e);
+        (axum::http::StatusCode::INTERNAL_SERVER_ERROR, String::new())
+    })
 }
 
-fn build_finance_report_csv(items: &[repository::Model]) -> anyhow::Result<Vec<u8>> {
-    let mut buffer: Vec<u8> = Vec::new();
-    // UTF-8 BOM — корректная кириллица при открытии в Excel.
-    buffer.extend_from_slice("\u{FEFF}".as_bytes());
-
-    let mut wtr = csv::WriterBuilder::new()
-        .delimiter(b';')
-        .from_writer(&mut buffer);
-
-    wtr.write_record(EXPORT_HEADERS)?;
-
-    for m in items {
-        let row: [String; 37] = [
-            m.id.clone(),
-            m.rr_dt.clone(),
-            m.rrd_id.to_string(),
-            m.source_row_ref.clone(),
-            m.connection_mp_ref.clone(),
-            m.organization_ref.clone(),
-            fmt_opt_f64(m.acquiring_fee),
-            fmt_opt_f64(m.acquiring_percent),
-            fmt_opt_f64(m.additional_payment),
-            m.bonus_type_name.clone().unwrap_or_default(),
-            fmt_opt_f64(m.commission_percent),
-            fmt_opt_f64(m.delivery_amount),
-            fmt_opt_f64(m.delivery_rub),
-            m.nm_id.map(|v| v.to_string()).unwrap_or_default(),
-            m.a004_nomenclature_ref.clone().unwrap_or_default(),
-            fmt_opt_f64(m.penalty),
-            fmt_opt_f64(m.ppvz_vw),
-            fmt_opt_f64(m.ppvz_vw_nds),
-            fmt_opt_f64(m.ppvz_sales_commission),
-            m.quantity.map(|v| v.to_string()).unwrap_or_default(),
-            fmt_opt_f64(m.rebill_logistic_cost),
-            fmt_opt_f64(m.retail_amount),
-            fmt_opt_f64(m.retail_price),
-            fmt_opt_f64(m.retail_price_withdisc_rub),
-            fmt_opt_f64(m.return_amount),
-            m.sa_name.clone().unwrap_or_default(),
-            fmt_opt_f64(m.storage_fee),
-            m.subject_name.clone().unwrap_or_default(),
-            m.supplier_oper_name.clone().unwrap_or_default(),
-            fmt_opt_f64(m.cashback_amount),
-            fmt_opt_f64(m.ppvz_for_pay),
-            fmt_opt_f64(m.ppvz_kvw_prc),
-            fmt_opt_f64(m.ppvz_kvw_prc_base),
-            m.srv_dbs.map(|v| v.to_string()).unwrap_or_default(),
-            m.srid.clone().unwrap_or_default(),
-            m.loaded_at_utc.clone(),
-            m.payload_version.to_string(),
-        ];
-        wtr.write_record(&row)?;
-    }
-
-    wtr.flush()?;
-    drop(wtr);
-    Ok(buffer)
+/// GET /api/p903/finance-report/export?format=csv&... — потоковая выгрузка по фильтрам
+/// списка, без фоновой задачи; колонки — как в фоновой выгрузке.
+pub async fn export_reports_csv(
+    active_org: ActiveOrganization,
+    Query(format): Query<crate::shared::export::csv::ExportFormatQuery>,
+    Query(mut req): Query<WbFinanceReportListRequest>,
+) -> Result<axum::response::Response, (axum::http::StatusCode, String)> {
+    use crate::projections::p903_wb_finance_report::export::{model_to_row, EXPORT_HEADERS};
+    use crate::shared::export::csv;
+
+    req.organization_ref = active_org.scope(req.organization_ref.take());
+    format.ensure_csv()?;
+    let filename = format!("wb_finance_report_{}_{}.csv", req.date_from, req.date_to);
+    let req = std::sync::Arc::new(req);
+    let pages = csv::paged(move |offset, limit| {
+        let req = req.clone();
+        async move {
+            let (items, _) = repository::list_with_filters(
+                &req.date_from,
+                &req.date_to,
+                req.nm_id,
+                req.sa_name.clone(),
+                req.connection_mp_ref.clone(),
+                req.organization_ref.clone(),
+                req.supplier_oper_name.clone(),
+                req.srid.clone(),
+                &req.sort_by,
+                req.sort_desc,
+                limit as i32,
+                offset as i32,
+            )
+            .await?;
+            Ok(items.iter().map(model_to_row).collect())
+        }
+    });
+    Ok(csv::csv_response(&filename, EXPORT_HEADERS, pages))
 }
 
 /// Handler для получения детальной информации по композитному ключу
@@ -240,76 +145,61 @@ pub struct OperationKindsQuery {
 }
 
 pub async fn list_operation_kinds(
+    active_org: ActiveOrganization,
     Query(query): Query<OperationKindsQuery>,
-) -> Result<Json<Vec<String>>, axum::http::StatusCode> {
+) -> Result<Json<Vec<String>>, AppError> {
     let items = repository::list_distinct_supplier_oper_names(
         &query.date_from,
         &query.date_to,
         query.connection_mp_ref,
-        query.organization_ref,
+        active_org.scope(query.organization_ref),
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to list finance report operation kinds: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list finance report operation kinds")?;
 
     Ok(Json(items))
 }
 
 pub async fn get_report_detail_by_id(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<WbFinanceReportDetailResponse>, axum::http::StatusCode> {
-    load_report_detail_by_id(&id).await.map(Json)
+) -> Result<Json<WbFinanceReportDetailResponse>, AppError> {
+    load_report_detail_by_id(&id, &active_org).await.map(Json)
 }
 
 pub async fn post_report_by_id(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<Json<WbFinanceReportDetailResponse>, axum::http::StatusCode> {
+) -> Result<Json<WbFinanceReportDetailResponse>, AppError> {
     let item = repository::get_by_id(&id)
         .await
-        .map_err(|e| {
-            tracing::error!(
-                "Failed to get finance report detail before post by id: {}",
-                e
-            );
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+        .context("Failed to get finance report detail before post by id")?
+        .filter(|item| active_org.allows(&item.organization_ref))
+        .ok_or_else(|| AppError::not_found("Запись не найдена"))?;
 
-    let day = NaiveDate::parse_from_str(&item.rr_dt, "%Y-%m-%d").map_err(|e| {
-        tracing::error!(
-            "Failed to parse p903 rr_dt '{}' for post by id: {}",
-            item.rr_dt,
-            e
-        );
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let day = NaiveDate::parse_from_str(&item.rr_dt, "%Y-%m-%d")
+        .with_context(|| format!("Failed to parse p903 rr_dt '{}' for post by id", item.rr_dt))?;
 
     crate::projections::p903_wb_finance_report::service::rebuild_day_from_existing(
         &item.connection_mp_ref,
         day,
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to rebuild p903 general ledger for id {}: {}", id, e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .with_context(|| format!("Failed to rebuild p903 general ledger for id {}", id))?;
 
-    load_report_detail_by_id(&id).await.map(Json)
+    load_report_detail_by_id(&id, &active_org).await.map(Json)
 }
 
 /// Handler для получения raw JSON по композитному ключу
 pub async fn get_raw_json_by_id(
+    active_org: ActiveOrganization,
     axum::extract::Path(id): axum::extract::Path<String>,
-) -> Result<String, axum::http::StatusCode> {
+) -> Result<String, AppError> {
     let item = repository::get_by_id(&id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get finance report raw json by id: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+        .context("Failed to get finance report raw json by id")?
+        .filter(|item| active_org.allows(&item.organization_ref))
+        .ok_or_else(|| AppError::not_found("Запись не найдена"))?;
 
     Ok(item.extra.unwrap_or_else(|| "{}".to_string()))
 }
@@ -321,40 +211,56 @@ pub struct SearchBySridQuery {
 }
 
 pub async fn search_by_srid(
+    active_org: ActiveOrganization,
     Query(query): Query<SearchBySridQuery>,
-) -> Result<Json<Vec<WbFinanceReportDto>>, axum::http::StatusCode> {
-    let items = repository::search_by_srid(&query.srid).await.map_err(|e| {
-        tracing::error!("Failed to search finance report by srid: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+) -> Result<Json<Vec<WbFinanceReportDto>>, AppError> {
+    let items = repository::search_by_srid(&query.srid)
+        .await
+        .context("Failed to search finance report by srid")?;
 
     let dtos: Vec<WbFinanceReportDto> = items
         .into_iter()
+        .filter(|item| active_org.allows(&item.organization_ref))
         .map(|item| model_to_dto(item, 0))
         .collect();
 
     Ok(Json(dtos))
 }
 
+/// Итоги по srid для вкладок связей a012/a015
+pub async fn totals_by_srid(
+    active_org: ActiveOrganization,
+    Query(query): Query<SearchBySridQuery>,
+) -> Result<Json<WbFinanceReportSridTotals>, AppError> {
+    let totals = repository::totals_by_srid(&query.srid, active_org.0.as_deref())
+        .await
+        .context("Failed to compute finance report totals by srid")?;
+
+    Ok(Json(WbFinanceReportSridTotals {
+        count: totals.count,
+        ppvz_vw: totals.ppvz_vw,
+        ppvz_vw_nds: totals.ppvz_vw_nds,
+        retail_amount: totals.retail_amount,
+        ppvz_for_pay: totals.ppvz_for_pay,
+        acquiring_fee: totals.acquiring_fee,
+    }))
+}
+
 /// Преобразование Model в DTO для списка (без extra для экономии трафика)
 async fn load_report_detail_by_id(
     id: &str,
-) -> Result<WbFinanceReportDetailResponse, axum::http::StatusCode> {
+    active_org: &ActiveOrganization,
+) -> Result<WbFinanceReportDetailResponse, AppError> {
     let item = repository::get_by_id(id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get finance report detail by id: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+        .context("Failed to get finance report detail by id")?
+        .filter(|item| active_org.allows(&item.organization_ref))
+        .ok_or_else(|| AppError::not_found("Запись не найдена"))?;
 
     let general_ledger_entries =
         crate::general_ledger::repository::list_by_registrator_ref(&item.id)
             .await
-            .map_err(|e| {
-                tracing::error!("Failed to load p903 general ledger rows by id: {}", e);
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR
-            })?
+            .context("Failed to load p903 general ledger rows by id")?
             .into_iter()
             .map(to_general_ledger_dto)
             .collect::<Vec<_>>();
@@ -369,7 +275,7 @@ async fn load_report_detail_by_id(
     })
 }
 
-fn model_to_dto(
+pub(crate) fn model_to_dto(
     model: repository::Model,
     general_ledger_entries_count: usize,
 ) -> WbFinanceReportDto {
@@ -1,9 +1,16 @@
-use axum::{extract::Query, http::StatusCode, Json};
-use contracts::projections::p904_sales_data::dto::{SalesDataDto, SalesDataListResponse};
+use anyhow::Context;
+use axum::{extract::Query, Json};
+use contracts::projections::p904_sales_data::dto::{
+    ReturnNettingSettings, SalesDataDto, SalesDataListResponse, SalesDataTotalsDto,
+    UnmatchedReturnsResponse,
+};
+use contracts::projections::source_document::source_aggregate;
 use serde::Deserialize;
 
-use crate::projections::p904_sales_data::repository::ModelWithCabinet;
-use crate::projections::p904_sales_data::service;
+use crate::projections::p904_sales_data::repository::{ModelWithCabinet, SalesDataTotals};
+use crate::projections::p904_sales_data::{return_netting, service};
+use crate::shared::error::AppError;
+use crate::system::org_context::ActiveOrganization;
 
 #[derive(Deserialize)]
 pub struct ListParams {
@@ -14,8 +21,9 @@ pub struct ListParams {
 }
 
 pub async fn list(
+    active_org: ActiveOrganization,
     Query(params): Query<ListParams>,
-) -> Result<Json<SalesDataListResponse>, StatusCode> {
+) -> Result<Json<SalesDataListResponse>, AppError> {
     // Валидация лимита: минимум 100, максимум 100000, по умолчанию 1000
     let limit = match params.limit {
         Some(lim) if lim < 100 => {
@@ -44,10 +52,22 @@ pub async fn list(
         params.connection_mp_ref
     );
 
+    let organization_ref = active_org.scope(None);
+    let totals = service::totals_with_filters(
+        params.date_from.clone(),
+        params.date_to.clone(),
+        params.connection_mp_ref.clone(),
+        organization_ref.clone(),
+        limit,
+    )
+    .await
+    .context("Failed to compute sales data totals")?;
+
     match service::list_with_filters(
         params.date_from,
         params.date_to,
         params.connection_mp_ref,
+        organization_ref,
         limit,
     )
     .await
@@ -62,19 +82,72 @@ pub async fn list(
                 items: dtos,
                 total_count,
                 has_more: false,
+                totals: totals_to_dto(totals),
             }))
         }
-        Err(e) => {
-            tracing::error!("Failed to list sales data: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+        Err(e) => Err(e.context("Failed to list sales data").into()),
     }
 }
 
+pub async fn get_return_netting_settings() -> Result<Json<ReturnNettingSettings>, AppError> {
+    crate::system::settings::service::get_p904_return_netting_mode()
+        .await
+        .map(|mode| Json(ReturnNettingSettings { mode }))
+        .context("Failed to get P904 return netting settings")
+}
+
+/// Смена режима действует на следующие проведения; уже проведённые возвраты
+/// нужно перепровести (u508), чтобы пересчитать их строки.
+pub async fn set_return_netting_settings(
+    Json(dto): Json<ReturnNettingSettings>,
+) -> Result<Json<ReturnNettingSettings>, AppError> {
+    crate::system::settings::service::set_p904_return_netting_mode(dto.mode)
+        .await
+        .context("Failed to update P904 return netting settings")?;
+    Ok(Json(dto))
+}
+
+#[derive(Deserialize)]
+pub struct UnmatchedReturnsParams {
+    pub date_from: String,
+    pub date_to: String,
+}
+
+/// Проведённые возвраты WB без проведённой продажи (не участвуют во взаимозачёте)
+pub async fn list_unmatched_returns(
+    Query(params): Query<UnmatchedReturnsParams>,
+) -> Result<Json<UnmatchedReturnsResponse>, AppError> {
+    return_netting::list_unmatched(&params.date_from, &params.date_to)
+        .await
+        .map(Json)
+        .context("Failed to list unmatched returns")
+}
+
 /// Преобразование ModelWithCabinet в DTO
+fn totals_to_dto(t: SalesDataTotals) -> SalesDataTotalsDto {
+    SalesDataTotalsDto {
+        count: t.count,
+        customer_in: t.customer_in,
+        customer_out: t.customer_out,
+        coinvest_in: t.coinvest_in,
+        commission_out: t.commission_out,
+        acquiring_out: t.acquiring_out,
+        penalty_out: t.penalty_out,
+        logistics_out: t.logistics_out,
+        seller_out: t.seller_out,
+        price_full: t.price_full,
+        price_list: t.price_list,
+        price_return: t.price_return,
+        commission_percent: t.commission_percent,
+        coinvest_persent: t.coinvest_persent,
+        total: t.total,
+    }
+}
+
 fn model_to_dto(model: ModelWithCabinet) -> SalesDataDto {
     SalesDataDto {
         id: model.base.id,
+        source_type: source_aggregate(&model.base.registrator_type).map(str::to_string),
         registrator_ref: model.base.registrator_ref,
         registrator_type: model.base.registrator_type,
         date: model.base.date,
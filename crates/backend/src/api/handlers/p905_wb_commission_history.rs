@@ -1,3 +1,4 @@
+use anyhow::Context;
 use axum::{
     extract::{Path, Query},
     Json,
@@ -8,11 +9,12 @@ use contracts::projections::p905_wb_commission_history::dto::{
 };
 
 use crate::projections::p905_wb_commission_history::repository;
+use crate::shared::error::AppError;
 
 /// Handler для получения списка комиссий с фильтрами
 pub async fn list_commissions(
     Query(req): Query<CommissionListRequest>,
-) -> Result<Json<CommissionListResponse>, axum::http::StatusCode> {
+) -> Result<Json<CommissionListResponse>, AppError> {
     let (items, total) = repository::list_with_filters(
         req.date_from,
         req.date_to,
@@ -23,10 +25,7 @@ pub async fn list_commissions(
         req.offset.unwrap_or(0),
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to list commissions: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list commissions")?;
 
     let dtos: Vec<CommissionHistoryDto> = items.into_iter().map(model_to_dto).collect();
 
@@ -39,14 +38,11 @@ pub async fn list_commissions(
 /// Handler для получения комиссии по ID
 pub async fn get_commission(
     Path(id): Path<String>,
-) -> Result<Json<CommissionHistoryDto>, axum::http::StatusCode> {
+) -> Result<Json<CommissionHistoryDto>, AppError> {
     let item = repository::get_by_id(&id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to get commission: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+        .context("Failed to get commission")?
+        .ok_or_else(|| AppError::not_found("Запись не найдена"))?;
 
     Ok(Json(model_to_dto(item)))
 }
@@ -54,7 +50,7 @@ pub async fn get_commission(
 /// Handler для создания/обновления комиссии
 pub async fn save_commission(
     Json(req): Json<CommissionSaveRequest>,
-) -> Result<Json<CommissionSaveResponse>, axum::http::StatusCode> {
+) -> Result<Json<CommissionSaveResponse>, AppError> {
     // Генерируем raw_json если не предоставлен
     let raw_json = req.raw_json.unwrap_or_else(|| {
         serde_json::json!({
@@ -73,7 +69,7 @@ pub async fn save_commission(
     });
 
     let date = chrono::NaiveDate::parse_from_str(&req.date, "%Y-%m-%d")
-        .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+        .map_err(|_| AppError::bad_request("Некорректная дата"))?;
 
     let is_new = req.id.is_none();
     let id = req
@@ -98,10 +94,9 @@ pub async fn save_commission(
         payload_version: 1,
     };
 
-    repository::upsert_entry(&entry).await.map_err(|e| {
-        tracing::error!("Failed to save commission: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    repository::upsert_entry(&entry)
+        .await
+        .context("Failed to save commission")?;
 
     let message = if is_new {
         "Commission record created successfully"
@@ -118,11 +113,10 @@ pub async fn save_commission(
 /// Handler для удаления комиссии
 pub async fn delete_commission(
     Path(id): Path<String>,
-) -> Result<Json<CommissionDeleteResponse>, axum::http::StatusCode> {
-    let deleted = repository::delete_by_id(&id).await.map_err(|e| {
-        tracing::error!("Failed to delete commission: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+) -> Result<Json<CommissionDeleteResponse>, AppError> {
+    let deleted = repository::delete_by_id(&id)
+        .await
+        .context("Failed to delete commission")?;
 
     if deleted == 0 {
         return Ok(Json(CommissionDeleteResponse {
@@ -139,7 +133,7 @@ pub async fn delete_commission(
 
 /// Handler для синхронизации комиссий с API
 /// DEPRECATED: Используйте u504 Import from Wildberries вместо этого
-pub async fn sync_commissions() -> Result<Json<CommissionSyncResponse>, axum::http::StatusCode> {
+pub async fn sync_commissions() -> Result<Json<CommissionSyncResponse>, AppError> {
     Ok(Json(CommissionSyncResponse {
         status: "deprecated".to_string(),
         message: "Эта функция устарела. Используйте 'Импорт из Wildberries' (u504) и выберите 'p905_wb_commission_history' для синхронизации комиссий.".to_string(),
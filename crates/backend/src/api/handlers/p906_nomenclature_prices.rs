@@ -1,9 +1,11 @@
-use axum::{extract::Query, http::StatusCode, Json};
+use anyhow::Context;
+use axum::{extract::Query, Json};
 use serde::Deserialize;
 
 use crate::projections::p906_nomenclature_prices::excel_import;
 use crate::projections::p906_nomenclature_prices::repository::PriceWithNomenclature;
 use crate::projections::p906_nomenclature_prices::service;
+use crate::shared::error::AppError;
 
 #[derive(Deserialize)]
 pub struct ListParams {
@@ -23,7 +25,7 @@ pub struct ListResponse {
 }
 
 /// GET /api/p906/nomenclature-prices
-pub async fn list(Query(params): Query<ListParams>) -> Result<Json<ListResponse>, StatusCode> {
+pub async fn list(Query(params): Query<ListParams>) -> Result<Json<ListResponse>, AppError> {
     // Валидация лимита: минимум 10, максимум 10000, по умолчанию 1000
     let limit = match params.limit {
         Some(lim) if lim < 10 => {
@@ -71,25 +73,19 @@ pub async fn list(Query(params): Query<ListParams>) -> Result<Json<ListResponse>
             );
             Ok(Json(ListResponse { items, total_count }))
         }
-        Err(e) => {
-            tracing::error!("Failed to list nomenclature prices: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+        Err(e) => Err(e.context("Failed to list nomenclature prices").into()),
     }
 }
 
 /// GET /api/p906/periods
 /// Возвращает список уникальных периодов для фильтра в UI
-pub async fn get_periods() -> Result<Json<Vec<String>>, StatusCode> {
+pub async fn get_periods() -> Result<Json<Vec<String>>, AppError> {
     match service::get_unique_periods().await {
         Ok(periods) => {
             tracing::info!("P906 periods response: {} unique periods", periods.len());
             Ok(Json(periods))
         }
-        Err(e) => {
-            tracing::error!("Failed to get unique periods: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+        Err(e) => Err(e.context("Failed to get unique periods").into()),
     }
 }
 
@@ -97,20 +93,15 @@ pub async fn get_periods() -> Result<Json<Vec<String>>, StatusCode> {
 /// Импортирует данные цен из Excel файла
 pub async fn import_excel(
     Json(excel_data): Json<excel_import::ExcelData>,
-) -> Result<Json<contracts::projections::p906_nomenclature_prices::excel::ImportResult>, StatusCode>
-{
+) -> Result<Json<contracts::projections::p906_nomenclature_prices::excel::ImportResult>, AppError> {
     tracing::info!(
         "Received Excel import request with {} rows",
         excel_data.metadata.row_count
     );
 
     // Импортируем данные из ExcelData
-    let result = match excel_import::import_prices_from_excel_data(excel_data).await {
-        Ok(result) => result,
-        Err(e) => {
-            tracing::error!("Excel import error: {}", e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let result = excel_import::import_prices_from_excel_data(excel_data)
+        .await
+        .context("Excel import error")?;
     Ok(Json(result))
 }
@@ -1,3 +1,4 @@
+use anyhow::Context;
 use axum::{
     extract::{Path, Query},
     Json,
@@ -10,12 +11,15 @@ use contracts::projections::p907_ym_payment_report::dto::{
 use serde::Deserialize;
 
 use crate::projections::p907_ym_payment_report::repository;
+use crate::shared::error::AppError;
+use crate::system::org_context::ActiveOrganization;
 use crate::usecases::u503_import_from_yandex::processors::payment_report as payment_report_processor;
 
 /// Handler для получения списка записей отчёта по платежам YM
 pub async fn list_reports(
+    active_org: ActiveOrganization,
     Query(req): Query<YmPaymentReportListRequest>,
-) -> Result<Json<YmPaymentReportListResponse>, axum::http::StatusCode> {
+) -> Result<Json<YmPaymentReportListResponse>, AppError> {
     let (items, total) = repository::list_with_filters(
         &req.date_from,
         &req.date_to,
@@ -26,26 +30,20 @@ pub async fn list_reports(
         req.order_id,
         req.bank_order_id,
         req.connection_mp_ref,
-        req.organization_ref,
+        active_org.scope(req.organization_ref),
         &req.sort_by,
         req.sort_desc,
         req.limit,
         req.offset,
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to list YM payment report: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list YM payment report")?;
 
     let gl_counts = crate::general_ledger::repository::count_by_registrator_refs(
         &items.iter().map(|item| item.id.clone()).collect::<Vec<_>>(),
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to count p907 general ledger rows: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to count p907 general ledger rows")?;
 
     let dtos: Vec<YmPaymentReportDto> = items
         .into_iter()
@@ -74,20 +72,18 @@ pub struct FilterOptionsQuery {
 }
 
 pub async fn filter_options(
+    active_org: ActiveOrganization,
     Query(req): Query<FilterOptionsQuery>,
-) -> Result<Json<YmPaymentReportFilterOptionsResponse>, axum::http::StatusCode> {
+) -> Result<Json<YmPaymentReportFilterOptionsResponse>, AppError> {
     let (transaction_types, payment_statuses, transaction_sources) =
         repository::list_filter_options(
             &req.date_from,
             &req.date_to,
             req.connection_mp_ref,
-            req.organization_ref,
+            active_org.scope(req.organization_ref),
         )
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to list YM payment report filter options: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to list YM payment report filter options")?;
 
     Ok(Json(YmPaymentReportFilterOptionsResponse {
         transaction_types,
@@ -98,34 +94,32 @@ pub async fn filter_options(
 
 /// Handler для получения одной записи отчёта по платежам YM по UUID (`id` поле).
 pub async fn get_report(
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
-) -> Result<Json<YmPaymentReportDetailResponse>, axum::http::StatusCode> {
-    load_report_detail_by_id(&id).await.map(Json)
+) -> Result<Json<YmPaymentReportDetailResponse>, AppError> {
+    load_report_detail_by_id(&id, &active_org).await.map(Json)
 }
 
 pub async fn post_report(
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
-) -> Result<Json<YmPaymentReportDetailResponse>, axum::http::StatusCode> {
+) -> Result<Json<YmPaymentReportDetailResponse>, AppError> {
+    // Запись другой организации не перепроводится — как несуществующая
+    load_report_detail_by_id(&id, &active_org).await?;
     crate::projections::p907_ym_payment_report::service::rebuild_entry_from_existing(&id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to rebuild p907 general ledger for id {}: {}", id, e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .with_context(|| format!("Failed to rebuild p907 general ledger for id {}", id))?;
 
-    load_report_detail_by_id(&id).await.map(Json)
+    load_report_detail_by_id(&id, &active_org).await.map(Json)
 }
 
 /// Массовое перепроведение всех записей p907: перестраивает GL/p914 для каждой
 /// строки. Используется после изменения маппинга оборотов, чтобы провести ранее
 /// не отражавшиеся операции YM.
-pub async fn repost_all() -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+pub async fn repost_all() -> Result<Json<serde_json::Value>, AppError> {
     let (rows, gl_entries) = crate::projections::p907_ym_payment_report::service::repost_all()
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to repost all p907 rows: {}", e);
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to repost all p907 rows")?;
 
     Ok(Json(serde_json::json!({
         "success": true,
@@ -138,24 +132,21 @@ pub async fn repost_all() -> Result<Json<serde_json::Value>, axum::http::StatusC
 /// Строки проекции p914 (слой `fina`), относящиеся к данной записи p907.
 /// Используется на детальной странице для показа соответствующего JSON.
 pub async fn get_finance_turnovers(
+    active_org: ActiveOrganization,
     Path(id): Path<String>,
 ) -> Result<
     Json<Vec<contracts::projections::p914_mp_finance_turnovers::dto::MpFinanceTurnoverDto>>,
-    axum::http::StatusCode,
+    AppError,
 > {
+    if active_org.0.is_some() {
+        load_report_detail_by_id(&id, &active_org).await?;
+    }
     let rows = crate::projections::p914_mp_finance_turnovers::repository::list_by_registrator(
         "p907_ym_payment_report",
         &id,
     )
     .await
-    .map_err(|e| {
-        tracing::error!(
-            "Failed to load p914 finance turnovers for p907 id {}: {}",
-            id,
-            e
-        );
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .with_context(|| format!("Failed to load p914 finance turnovers for p907 id {}", id))?;
 
     let dtos = rows
         .into_iter()
@@ -167,7 +158,7 @@ pub async fn get_finance_turnovers(
 
 /// Migrate all SYNTH_... record keys to ymid_... format.
 /// Safe to call multiple times — already-migrated rows are skipped.
-pub async fn migrate_keys() -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+pub async fn migrate_keys() -> Result<Json<serde_json::Value>, AppError> {
     let (migrated, _already_ymid, errors) = repository::migrate_synth_keys(|record| {
         payment_report_processor::build_ymid_key(
             record.order_id,
@@ -178,10 +169,7 @@ pub async fn migrate_keys() -> Result<Json<serde_json::Value>, axum::http::Statu
         )
     })
     .await
-    .map_err(|e| {
-        tracing::error!("migrate_keys: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("migrate_keys")?;
 
     Ok(Json(serde_json::json!({
         "success": true,
@@ -193,23 +181,21 @@ pub async fn migrate_keys() -> Result<Json<serde_json::Value>, axum::http::Statu
 
 async fn load_report_detail_by_id(
     id: &str,
-) -> Result<YmPaymentReportDetailResponse, axum::http::StatusCode> {
-    let item = repository::get_by_uuid(id).await.map_err(|e| {
-        tracing::error!("Failed to get YM payment report detail: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    active_org: &ActiveOrganization,
+) -> Result<YmPaymentReportDetailResponse, AppError> {
+    let item = repository::get_by_uuid(id)
+        .await
+        .context("Failed to get YM payment report detail")?
+        .filter(|item| active_org.allows(&item.organization_ref));
 
     let Some(item) = item else {
-        return Err(axum::http::StatusCode::NOT_FOUND);
+        return Err(AppError::not_found("Запись не найдена"));
     };
 
     let general_ledger_entries =
         crate::general_ledger::repository::list_by_registrator("p907_ym_payment_report", &item.id)
             .await
-            .map_err(|e| {
-                tracing::error!("Failed to load p907 general ledger rows by id: {}", e);
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR
-            })?
+            .context("Failed to load p907 general ledger rows by id")?
             .into_iter()
             .map(to_general_ledger_dto)
             .collect::<Vec<_>>();
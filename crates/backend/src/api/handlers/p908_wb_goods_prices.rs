@@ -1,3 +1,4 @@
+use anyhow::Context;
 use axum::{
     extract::{Path, Query},
     Json,
@@ -7,11 +8,12 @@ use contracts::projections::p908_wb_goods_prices::dto::{
 };
 
 use crate::projections::p908_wb_goods_prices::repository::{self, WbGoodsPriceRow};
+use crate::shared::error::AppError;
 
 /// Handler для получения списка цен товаров WB
 pub async fn list_goods_prices(
     Query(req): Query<WbGoodsPriceListRequest>,
-) -> Result<Json<WbGoodsPriceListResponse>, axum::http::StatusCode> {
+) -> Result<Json<WbGoodsPriceListResponse>, AppError> {
     let (items, total) = repository::list_with_filters(
         req.connection_mp_ref,
         req.vendor_code,
@@ -22,10 +24,7 @@ pub async fn list_goods_prices(
         req.offset,
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to list WB goods prices: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list WB goods prices")?;
 
     let dtos: Vec<WbGoodsPriceDto> = items.into_iter().map(row_to_dto).collect();
     let has_more = total > (req.offset + dtos.len() as i32);
@@ -38,17 +37,14 @@ pub async fn list_goods_prices(
 }
 
 /// Handler для получения одной записи цены товара WB по nm_id
-pub async fn get_goods_price(
-    Path(nm_id): Path<i64>,
-) -> Result<Json<WbGoodsPriceDto>, axum::http::StatusCode> {
-    let item = repository::get_by_nm_id(nm_id).await.map_err(|e| {
-        tracing::error!("Failed to get WB goods price: {}", e);
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+pub async fn get_goods_price(Path(nm_id): Path<i64>) -> Result<Json<WbGoodsPriceDto>, AppError> {
+    let item = repository::get_by_nm_id(nm_id)
+        .await
+        .context("Failed to get WB goods price")?;
 
     match item {
         Some(model) => Ok(Json(model_to_dto(model))),
-        None => Err(axum::http::StatusCode::NOT_FOUND),
+        None => Err(AppError::not_found("Запись не найдена")),
     }
 }
 
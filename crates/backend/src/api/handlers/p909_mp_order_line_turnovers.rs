@@ -1,6 +1,6 @@
+use anyhow::Context;
 use axum::{
     extract::{Path, Query},
-    http::StatusCode,
     Json,
 };
 use contracts::projections::p909_mp_order_line_turnovers::dto::{
@@ -9,6 +9,8 @@ use contracts::projections::p909_mp_order_line_turnovers::dto::{
 use contracts::shared::analytics::{AggKind, EventKind, TurnoverLayer, ValueKind};
 use serde::Deserialize;
 
+use crate::shared::error::AppError;
+
 #[derive(Deserialize)]
 pub struct ListParams {
     pub limit: Option<u64>,
@@ -27,7 +29,7 @@ pub struct ListParams {
 
 pub async fn list(
     Query(params): Query<ListParams>,
-) -> Result<Json<MpOrderLineTurnoverListResponse>, StatusCode> {
+) -> Result<Json<MpOrderLineTurnoverListResponse>, AppError> {
     let limit = params.limit.or(Some(1000));
     let offset = params.offset.or(Some(0));
     let sort_desc = params.sort_desc.unwrap_or(true);
@@ -43,10 +45,7 @@ pub async fn list(
             params.link_status.clone(),
         )
         .await
-        .map_err(|error| {
-            tracing::error!("Failed to count p909 rows: {}", error);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to count p909 rows")?;
     let items = crate::projections::p909_mp_order_line_turnovers::service::list_with_filters(
         params.date_from,
         params.date_to,
@@ -62,10 +61,7 @@ pub async fn list(
         limit,
     )
     .await
-    .map_err(|error| {
-        tracing::error!("Failed to list p909 rows: {}", error);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list p909 rows")?;
 
     let dtos = items.into_iter().map(model_to_dto).collect::<Vec<_>>();
     let limit_value = limit.unwrap_or(1000);
@@ -77,14 +73,11 @@ pub async fn list(
     }))
 }
 
-pub async fn get_by_id(Path(id): Path<String>) -> Result<Json<MpOrderLineTurnoverDto>, StatusCode> {
+pub async fn get_by_id(Path(id): Path<String>) -> Result<Json<MpOrderLineTurnoverDto>, AppError> {
     let model = crate::projections::p909_mp_order_line_turnovers::service::get_by_id(&id)
         .await
-        .map_err(|error| {
-            tracing::error!("Failed to load p909 detail '{}': {}", id, error);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .with_context(|| format!("Failed to load p909 detail '{}'", id))?
+        .ok_or_else(|| AppError::not_found("Запись не найдена"))?;
 
     Ok(Json(model_to_dto(model)))
 }
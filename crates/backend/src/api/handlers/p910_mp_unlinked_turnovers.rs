@@ -1,6 +1,6 @@
+use anyhow::Context;
 use axum::{
     extract::{Path, Query},
-    http::StatusCode,
     Json,
 };
 use contracts::projections::p910_mp_unlinked_turnovers::dto::{
@@ -9,6 +9,8 @@ use contracts::projections::p910_mp_unlinked_turnovers::dto::{
 use contracts::shared::analytics::{AggKind, TurnoverLayer, ValueKind};
 use serde::Deserialize;
 
+use crate::shared::error::AppError;
+
 #[derive(Deserialize)]
 pub struct ListParams {
     pub limit: Option<u64>,
@@ -25,7 +27,7 @@ pub struct ListParams {
 
 pub async fn list(
     Query(params): Query<ListParams>,
-) -> Result<Json<MpUnlinkedTurnoverListResponse>, StatusCode> {
+) -> Result<Json<MpUnlinkedTurnoverListResponse>, AppError> {
     let limit = params.limit.or(Some(1000));
     let offset = params.offset.or(Some(0));
     let sort_desc = params.sort_desc.unwrap_or(true);
@@ -38,10 +40,7 @@ pub async fn list(
         params.registrator_type.clone(),
     )
     .await
-    .map_err(|error| {
-        tracing::error!("Failed to count p910 rows: {}", error);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to count p910 rows")?;
     let items = crate::projections::p910_mp_unlinked_turnovers::service::list_with_filters(
         params.date_from,
         params.date_to,
@@ -55,10 +54,7 @@ pub async fn list(
         limit,
     )
     .await
-    .map_err(|error| {
-        tracing::error!("Failed to list p910 rows: {}", error);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list p910 rows")?;
 
     let dtos = items.into_iter().map(model_to_dto).collect::<Vec<_>>();
     let limit_value = limit.unwrap_or(1000);
@@ -70,14 +66,11 @@ pub async fn list(
     }))
 }
 
-pub async fn get_by_id(Path(id): Path<String>) -> Result<Json<MpUnlinkedTurnoverDto>, StatusCode> {
+pub async fn get_by_id(Path(id): Path<String>) -> Result<Json<MpUnlinkedTurnoverDto>, AppError> {
     let model = crate::projections::p910_mp_unlinked_turnovers::service::get_by_id(&id)
         .await
-        .map_err(|error| {
-            tracing::error!("Failed to load p910 detail '{}': {}", id, error);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .with_context(|| format!("Failed to load p910 detail '{}'", id))?
+        .ok_or_else(|| AppError::not_found("Запись не найдена"))?;
 
     Ok(Json(model_to_dto(model)))
 }
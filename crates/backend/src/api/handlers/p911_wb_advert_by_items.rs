@@ -1,6 +1,6 @@
+use anyhow::Context;
 use axum::{
     extract::{Path, Query},
-    http::StatusCode,
     Json,
 };
 use contracts::general_ledger::GeneralLedgerEntryDto;
@@ -9,6 +9,8 @@ use contracts::projections::p911_wb_advert_by_items::dto::{
 };
 use serde::Deserialize;
 
+use crate::shared::error::AppError;
+
 #[derive(Deserialize)]
 pub struct ListParams {
     pub limit: Option<u64>,
@@ -27,7 +29,7 @@ pub struct ListParams {
 
 pub async fn list(
     Query(params): Query<ListParams>,
-) -> Result<Json<WbAdvertByItemListResponse>, StatusCode> {
+) -> Result<Json<WbAdvertByItemListResponse>, AppError> {
     let limit = params.limit.or(Some(1000));
     let offset = params.offset.or(Some(0));
     let sort_desc = params.sort_desc.unwrap_or(true);
@@ -43,10 +45,7 @@ pub async fn list(
         params.general_ledger_ref.clone(),
     )
     .await
-    .map_err(|error| {
-        tracing::error!("Failed to count p911 rows: {}", error);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to count p911 rows")?;
 
     let items = crate::projections::p911_wb_advert_by_items::service::list_with_filters(
         params.date_from,
@@ -63,10 +62,7 @@ pub async fn list(
         limit,
     )
     .await
-    .map_err(|error| {
-        tracing::error!("Failed to list p911 rows: {}", error);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list p911 rows")?;
 
     let dtos = items.into_iter().map(model_to_dto).collect::<Vec<_>>();
     let limit_value = limit.unwrap_or(1000);
@@ -80,33 +76,24 @@ pub async fn list(
 
 pub async fn get_by_general_ledger_ref(
     Path(general_ledger_ref): Path<String>,
-) -> Result<Json<WbAdvertByItemDetailDto>, StatusCode> {
+) -> Result<Json<WbAdvertByItemDetailDto>, AppError> {
     let items = crate::projections::p911_wb_advert_by_items::service::list_by_general_ledger_ref(
         &general_ledger_ref,
     )
     .await
-    .map_err(|error| {
-        tracing::error!(
-            "Failed to load p911 detail '{}': {}",
-            general_ledger_ref,
-            error
-        );
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .with_context(|| format!("Failed to load p911 detail '{}'", general_ledger_ref))?;
 
     if items.is_empty() {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(AppError::not_found("Запись не найдена"));
     }
 
     let general_ledger_entry = crate::general_ledger::repository::get_by_id(&general_ledger_ref)
         .await
-        .map_err(|error| {
-            tracing::error!(
-                "Failed to load general_ledger entry '{}' for p911 detail: {}",
-                general_ledger_ref,
-                error
-            );
-            StatusCode::INTERNAL_SERVER_ERROR
+        .with_context(|| {
+            format!(
+                "Failed to load general_ledger entry '{}' for p911 detail",
+                general_ledger_ref
+            )
         })?
         .map(to_general_ledger_dto);
 
@@ -1,7 +1,9 @@
-use axum::{extract::Query, http::StatusCode, Json};
+use anyhow::Context;
+use axum::{extract::Query, Json};
 use serde::Deserialize;
 
 use crate::projections::p912_nomenclature_costs::service;
+use crate::shared::error::AppError;
 
 #[derive(Debug, Deserialize)]
 pub struct ListParams {
@@ -19,7 +21,7 @@ pub async fn list(
     Query(params): Query<ListParams>,
 ) -> Result<
     Json<contracts::projections::p912_nomenclature_costs::dto::NomenclatureCostListResponse>,
-    StatusCode,
+    AppError,
 > {
     let limit = params
         .limit
@@ -43,9 +45,8 @@ pub async fn list(
                 total_count,
             },
         )),
-        Err(error) => {
-            tracing::error!("Failed to list p912 nomenclature costs: {}", error);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+        Err(error) => Err(error
+            .context("Failed to list p912 nomenclature costs")
+            .into()),
     }
 }
@@ -1,9 +1,12 @@
-use axum::{extract::Query, http::StatusCode, Json};
+use anyhow::Context;
+use axum::{extract::Query, Json};
 use contracts::projections::p913_wb_advert_order_attr::dto::{
     WbAdvertOrderAttrDto, WbAdvertOrderAttrListResponse,
 };
 use serde::Deserialize;
 
+use crate::shared::error::AppError;
+
 #[derive(Deserialize)]
 pub struct ListParams {
     pub limit: Option<u64>,
@@ -20,7 +23,7 @@ pub struct ListParams {
 
 pub async fn list(
     Query(params): Query<ListParams>,
-) -> Result<Json<WbAdvertOrderAttrListResponse>, StatusCode> {
+) -> Result<Json<WbAdvertOrderAttrListResponse>, AppError> {
     let limit = params.limit.or(Some(500));
     let offset = params.offset.or(Some(0));
     let sort_desc = params.sort_desc.unwrap_or(true);
@@ -35,10 +38,7 @@ pub async fn list(
             params.wb_advert_campaign_code.clone(),
         )
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to count p913 rows: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to count p913 rows")?;
 
     let items = crate::projections::p913_wb_advert_order_attr::repository::list_with_filters(
         params.date_from,
@@ -53,10 +53,7 @@ pub async fn list(
         limit,
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to list p913 rows: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list p913 rows")?;
 
     let dtos = items.into_iter().map(model_to_dto).collect::<Vec<_>>();
     Ok(Json(WbAdvertOrderAttrListResponse {
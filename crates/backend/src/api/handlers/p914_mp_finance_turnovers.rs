@@ -1,9 +1,12 @@
-use axum::{extract::Query, http::StatusCode, Json};
+use anyhow::Context;
+use axum::{extract::Query, Json};
 use contracts::projections::p914_mp_finance_turnovers::dto::{
     MpFinanceTurnoverDto, MpFinanceTurnoverListResponse,
 };
 use serde::Deserialize;
 
+use crate::shared::error::AppError;
+
 #[derive(Deserialize)]
 pub struct ListParams {
     pub limit: Option<u64>,
@@ -21,7 +24,7 @@ pub struct ListParams {
 
 pub async fn list(
     Query(params): Query<ListParams>,
-) -> Result<Json<MpFinanceTurnoverListResponse>, StatusCode> {
+) -> Result<Json<MpFinanceTurnoverListResponse>, AppError> {
     let limit = params.limit.or(Some(1000));
     let offset = params.offset.or(Some(0));
     let sort_desc = params.sort_desc.unwrap_or(true);
@@ -37,10 +40,7 @@ pub async fn list(
             params.event_kind.clone(),
         )
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to count p914 rows: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to count p914 rows")?;
 
     let items = crate::projections::p914_mp_finance_turnovers::repository::list_with_filters(
         params.date_from,
@@ -56,10 +56,7 @@ pub async fn list(
         limit,
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to list p914 rows: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list p914 rows")?;
 
     let has_more = offset.unwrap_or(0) + (items.len() as u64) < total_count;
 
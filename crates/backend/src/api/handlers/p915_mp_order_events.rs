@@ -1,6 +1,6 @@
+use anyhow::Context;
 use axum::{
     extract::{Path, Query},
-    http::StatusCode,
     Json,
 };
 use contracts::projections::p915_mp_order_events::dto::{
@@ -8,6 +8,8 @@ use contracts::projections::p915_mp_order_events::dto::{
 };
 use serde::Deserialize;
 
+use crate::shared::error::AppError;
+
 #[derive(Deserialize)]
 pub struct ListParams {
     pub limit: Option<u64>,
@@ -25,7 +27,7 @@ pub struct ListParams {
 
 pub async fn list(
     Query(params): Query<ListParams>,
-) -> Result<Json<MpOrderEventListResponse>, StatusCode> {
+) -> Result<Json<MpOrderEventListResponse>, AppError> {
     let limit = params.limit.or(Some(1000));
     let offset = params.offset.or(Some(0));
     let sort_desc = params.sort_desc.unwrap_or(false);
@@ -40,10 +42,7 @@ pub async fn list(
         params.layer.clone(),
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to count p915 rows: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to count p915 rows")?;
 
     let items = crate::projections::p915_mp_order_events::repository::list_with_filters(
         params.date_from,
@@ -59,10 +58,7 @@ pub async fn list(
         limit,
     )
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to list p915 rows: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .context("Failed to list p915 rows")?;
 
     let has_more = offset.unwrap_or(0) + (items.len() as u64) < total_count;
 
@@ -77,13 +73,10 @@ pub async fn list(
 /// Полный таймлайн событий одного заказа (упорядочен по дате/типу события).
 pub async fn by_order(
     Path(order_id): Path<String>,
-) -> Result<Json<Vec<MpOrderEventDto>>, StatusCode> {
+) -> Result<Json<Vec<MpOrderEventDto>>, AppError> {
     let items = crate::projections::p915_mp_order_events::repository::list_by_order_id(&order_id)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to list p915 rows by order_id: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .context("Failed to list p915 rows by order_id")?;
     Ok(Json(items.into_iter().map(model_to_dto).collect()))
 }
 
@@ -0,0 +1,80 @@
+use anyhow::Context;
+use axum::{extract::Query, Json};
+use contracts::projections::p918_storage_cost_allocation::dto::{
+    StorageAllocationRebuildRequest, StorageAllocationRebuildResponse, StorageAllocationSettings,
+    StorageCostAllocationListResponse,
+};
+use serde::Deserialize;
+
+use crate::projections::p918_storage_cost_allocation::{repository, service};
+use crate::shared::error::AppError;
+
+#[derive(Deserialize)]
+pub struct ListParams {
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub connection_mp_ref: Option<String>,
+    pub turnover_code: Option<String>,
+    pub nm_id: Option<i64>,
+    pub source_ref: Option<String>,
+    #[serde(default)]
+    pub unallocated_only: bool,
+}
+
+/// GET /api/p918/storage-allocation — доли хранения и приёмки по SKU (след распределения).
+pub async fn list(
+    Query(params): Query<ListParams>,
+) -> Result<Json<StorageCostAllocationListResponse>, AppError> {
+    let query = repository::AllocationListQuery {
+        date_from: params.date_from,
+        date_to: params.date_to,
+        connection_mp_ref: params.connection_mp_ref,
+        turnover_code: params.turnover_code,
+        nm_id: params.nm_id,
+        source_ref: params.source_ref,
+        unallocated_only: params.unallocated_only,
+        offset: params.offset.unwrap_or(0),
+        limit: params.limit.unwrap_or(1000).min(5000),
+    };
+    let (items, total_count) = repository::list(&query)
+        .await
+        .context("Failed to list p918 rows")?;
+    Ok(Json(StorageCostAllocationListResponse {
+        items: items.into_iter().map(Into::into).collect(),
+        total_count,
+    }))
+}
+
+pub async fn get_settings() -> Result<Json<StorageAllocationSettings>, AppError> {
+    crate::system::settings::service::get_p918_storage_allocation_settings()
+        .await
+        .map(Json)
+        .context("Failed to get P918 allocation settings")
+}
+
+/// Новые правила действуют на следующие проведения; уже распределённый период
+/// пересчитывается через `rebuild`.
+pub async fn set_settings(
+    Json(dto): Json<StorageAllocationSettings>,
+) -> Result<Json<StorageAllocationSettings>, AppError> {
+    dto.validate().map_err(AppError::bad_request)?;
+    crate::system::settings::service::set_p918_storage_allocation_settings(&dto)
+        .await
+        .context("Failed to update P918 allocation settings")?;
+    Ok(Json(dto))
+}
+
+/// POST /api/p918/storage-allocation/rebuild — перераспределить период по текущим правилам.
+pub async fn rebuild(
+    Json(request): Json<StorageAllocationRebuildRequest>,
+) -> Result<Json<StorageAllocationRebuildResponse>, AppError> {
+    if request.date_from.is_empty() || request.date_to.is_empty() {
+        return Err(AppError::bad_request("Укажите период"));
+    }
+    let response = service::rebuild_range(&request.date_from, &request.date_to)
+        .await
+        .context("Failed to rebuild P918 allocations")?;
+    Ok(Json(response))
+}
@@ -0,0 +1,61 @@
+use anyhow::Context;
+use axum::{extract::Query, Json};
+use chrono::{Duration, Utc};
+use contracts::projections::p919_daily_sales_summary::dto::{
+    DailySalesSummaryRebuildResponse, DailySalesSummaryResponse, DEFAULT_PERIOD_DAYS,
+};
+use serde::Deserialize;
+
+use crate::projections::p919_daily_sales_summary::{repository, service};
+use crate::shared::error::AppError;
+
+#[derive(Deserialize)]
+pub struct ListParams {
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub marketplace: Option<String>,
+    pub organization_ref: Option<String>,
+}
+
+/// GET /api/p919/daily-summary — итоги по дням; без периода — последние `DEFAULT_PERIOD_DAYS` дней.
+pub async fn list(
+    Query(params): Query<ListParams>,
+) -> Result<Json<DailySalesSummaryResponse>, AppError> {
+    let today = Utc::now().date_naive();
+    let date_to = params
+        .date_to
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| today.format("%Y-%m-%d").to_string());
+    let date_from = params
+        .date_from
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| {
+            (today - Duration::days(DEFAULT_PERIOD_DAYS - 1))
+                .format("%Y-%m-%d")
+                .to_string()
+        });
+    if date_from > date_to {
+        return Err(AppError::bad_request("Начало периода позже окончания"));
+    }
+    let items = repository::list(
+        &date_from,
+        &date_to,
+        params.marketplace.as_deref(),
+        params.organization_ref.as_deref(),
+    )
+    .await
+    .context("Failed to list p919 rows")?;
+    Ok(Json(DailySalesSummaryResponse {
+        date_from,
+        date_to,
+        items: items.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// POST /api/p919/daily-summary/rebuild — пересобрать сводку по проведённым документам.
+pub async fn rebuild() -> Result<Json<DailySalesSummaryRebuildResponse>, AppError> {
+    let response = service::rebuild()
+        .await
+        .context("Failed to rebuild P919 summary")?;
+    Ok(Json(response))
+}
@@ -5,9 +5,12 @@ use axum::{
 use contracts::quality::{
     CheckDetails, CheckResult, NipCleanupRequest, NipCleanupResult, NipGroupsResponse,
     NipProjectionRow, NipRepostRequest, NipRepostResult, QualityCheckInfo, QualityCheckSource,
+    ViolationAcknowledgeRequest, ViolationAcknowledgeResult,
 };
 use serde::Deserialize;
 
+use crate::system::auth::extractor::CurrentUser;
+
 /// GET /api/quality/checks
 pub async fn list_checks() -> Json<Vec<QualityCheckInfo>> {
     Json(crate::quality::list_checks())
@@ -170,3 +173,22 @@ pub async fn cleanup_orphans(
         }
     }
 }
+
+/// POST /api/quality/checks/:id/acknowledge
+pub async fn acknowledge_violations(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+    Json(body): Json<ViolationAcknowledgeRequest>,
+) -> Result<Json<ViolationAcknowledgeResult>, axum::http::StatusCode> {
+    match crate::quality::acknowledge_violations(&id, &body, &claims.username).await {
+        Ok(result) => Ok(Json(result)),
+        Err(e) if e.to_string().starts_with("NOT_FOUND:") => {
+            tracing::warn!("Quality acknowledge not found: '{}': {}", id, e);
+            Err(axum::http::StatusCode::NOT_FOUND)
+        }
+        Err(e) => {
+            tracing::error!("acknowledge_violations '{}': {}", id, e);
+            Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
@@ -1,11 +1,18 @@
+use anyhow::Context;
 use axum::{
     extract::{Path, Query},
     Json,
 };
+use contracts::usecases::u505_match_nomenclature::suggestions::{
+    MatchAccuracyQuery, MatchAccuracyReport, MatchAutoAcceptRequest, MatchDecisionRequest,
+    MatchDecisionResponse, MatchSuggestionListResponse, MatchSuggestionQuery,
+};
 use once_cell::sync::Lazy;
 use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::shared::error::AppError;
+use crate::system::auth::extractor::CurrentUser;
 use crate::usecases;
 
 // ============================================================================
@@ -205,6 +212,61 @@ pub async fn u505_get_progress(
     }
 }
 
+/// GET /api/u505/suggestions — товары без связи и кандидаты номенклатуры.
+pub async fn u505_list_suggestions(
+    Query(query): Query<MatchSuggestionQuery>,
+) -> Result<Json<MatchSuggestionListResponse>, AppError> {
+    let response = usecases::u505_match_nomenclature::suggestions::list_suggestions(&query)
+        .await
+        .context("Failed to build u505 suggestions")?;
+    Ok(Json(response))
+}
+
+/// POST /api/u505/suggestions/decisions — принять / отклонить предложения пачкой.
+pub async fn u505_apply_decisions(
+    CurrentUser(claims): CurrentUser,
+    Json(request): Json<MatchDecisionRequest>,
+) -> Result<Json<MatchDecisionResponse>, AppError> {
+    if request.decisions.is_empty() {
+        return Err(AppError::bad_request("Нет решений"));
+    }
+    let response =
+        usecases::u505_match_nomenclature::suggestions::apply_decisions(&request, &claims.username)
+            .await
+            .context("Failed to apply u505 decisions")?;
+    Ok(Json(response))
+}
+
+/// POST /api/u505/suggestions/accept-high-confidence — принять все уверенные предложения.
+pub async fn u505_accept_high_confidence(
+    CurrentUser(claims): CurrentUser,
+    Json(request): Json<MatchAutoAcceptRequest>,
+) -> Result<Json<MatchDecisionResponse>, AppError> {
+    if request
+        .min_score
+        .is_some_and(|score| !(0.0..=1.0).contains(&score))
+    {
+        return Err(AppError::bad_request("Порог оценки должен быть от 0 до 1"));
+    }
+    let response = usecases::u505_match_nomenclature::suggestions::accept_high_confidence(
+        &request,
+        &claims.username,
+    )
+    .await
+    .context("Failed to accept high-confidence u505 suggestions")?;
+    Ok(Json(response))
+}
+
+/// GET /api/u505/suggestions/accuracy — точность предложений во времени.
+pub async fn u505_accuracy_report(
+    Query(query): Query<MatchAccuracyQuery>,
+) -> Result<Json<MatchAccuracyReport>, AppError> {
+    let report = usecases::u505_match_nomenclature::suggestions::accuracy_report(&query)
+        .await
+        .context("Failed to build u505 accuracy report")?;
+    Ok(Json(report))
+}
+
 // ============================================================================
 // UseCase u506: Import from LemanaPro
 // ============================================================================
@@ -335,6 +397,36 @@ pub async fn u508_start_aggregate_repost(
     }
 }
 
+/// POST /api/u508/repost/period/start — распровести и провести заново документы
+/// периода по нескольким агрегатам (после исправления сопоставлений).
+pub async fn u508_start_period_repost(
+    Json(request): Json<contracts::usecases::u508_repost_documents::PeriodRepostRequest>,
+) -> Result<Json<contracts::usecases::u508_repost_documents::RepostResponse>, axum::http::StatusCode>
+{
+    match REPOST_EXECUTOR.start_period_repost(request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("Failed to start period repost: {}", e);
+            Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// POST /api/projections/rebuild?projection=p900&from=...&to=... — пересборка
+/// проекции с нуля за период (очистка + перепроведение документов-источников).
+pub async fn u508_start_projection_rebuild(
+    Query(request): Query<contracts::usecases::u508_repost_documents::ProjectionRebuildRequest>,
+) -> Result<Json<contracts::usecases::u508_repost_documents::RepostResponse>, axum::http::StatusCode>
+{
+    match REPOST_EXECUTOR.start_projection_rebuild(request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            tracing::error!("Failed to start projection rebuild: {}", e);
+            Err(axum::http::StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
 /// GET /api/u508/repost/:session_id/progress
 pub async fn u508_get_progress(
     Path(session_id): Path<String>,
@@ -403,3 +495,52 @@ pub async fn u508_funnel_diagnostics(
         }
     }
 }
+
+static OZON_SKU_LINK_EXECUTOR: Lazy<Arc<usecases::u509_link_ozon_skus::LinkExecutor>> =
+    Lazy::new(|| {
+        let tracker = Arc::new(usecases::u509_link_ozon_skus::ProgressTracker::new());
+        Arc::new(usecases::u509_link_ozon_skus::LinkExecutor::new(tracker))
+    });
+
+/// POST /api/u509/link/start — привязать строки транзакций Ozon к a007 по SKU
+pub async fn u509_start_linking(
+    Json(request): Json<contracts::usecases::u509_link_ozon_skus::LinkRequest>,
+) -> Result<Json<contracts::usecases::u509_link_ozon_skus::LinkResponse>, axum::http::StatusCode> {
+    match OZON_SKU_LINK_EXECUTOR.start_linking(request).await {
+        Ok(response) => Ok(Json(response)),
+        Err(e) if e.to_string().contains("Invalid") => {
+            tracing::warn!("Rejected Ozon SKU linking request: {}", e);
+            Err(axum::http::StatusCode::BAD_REQUEST)
+        }
+        Err(e) => {
+            tracing::error!("Failed to start Ozon SKU linking: {}", e);
+            Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/u509/link/:session_id/progress
+pub async fn u509_get_progress(
+    Path(session_id): Path<String>,
+) -> Result<
+    Json<contracts::usecases::u509_link_ozon_skus::progress::LinkProgress>,
+    axum::http::StatusCode,
+> {
+    match OZON_SKU_LINK_EXECUTOR.get_progress(&session_id) {
+        Some(progress) => Ok(Json(progress)),
+        None => Err(axum::http::StatusCode::NOT_FOUND),
+    }
+}
+
+/// GET /api/u509/unmatched — SKU Ozon без товара маркетплейса
+pub async fn u509_unmatched_report(
+) -> Result<Json<contracts::usecases::u509_link_ozon_skus::UnmatchedSkuReport>, axum::http::StatusCode>
+{
+    match OZON_SKU_LINK_EXECUTOR.unmatched_report().await {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => {
+            tracing::error!("Failed to build unmatched Ozon SKU report: {}", e);
+            Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
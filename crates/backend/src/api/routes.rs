@@ -67,6 +67,7 @@ pub fn configure_business_routes() -> Router {
         .merge(u506_routes())
         .merge(u507_routes())
         .merge(u508_routes())
+        .merge(u509_routes())
         // Projections — each with their own scope
         .merge(p900_routes())
         .merge(p901_routes())
@@ -81,6 +82,8 @@ pub fn configure_business_routes() -> Router {
         .merge(p913_routes())
         .merge(p914_routes())
         .merge(p915_routes())
+        .merge(p918_routes())
+        .merge(p919_routes())
         // System views with scopes
         .merge(quality_routes())
         .merge(dashboard_routes())
@@ -370,6 +373,26 @@ fn a007_routes() -> Router {
             "/api/a007/marketplace-product",
             get(handlers::a007_marketplace_product::list_paginated),
         )
+        .route(
+            "/api/a007/marketplace-product/:id/price-history",
+            get(handlers::a007_marketplace_product::price_history),
+        )
+        .route(
+            "/api/a007/sales-anomalies",
+            get(handlers::a007_marketplace_product::list_sales_anomalies),
+        )
+        .route(
+            "/api/a007/sales-anomalies/:id/explain",
+            post(handlers::a007_marketplace_product::explain_sales_anomaly),
+        )
+        .route(
+            "/api/a007/cross-mapping",
+            get(handlers::a007_marketplace_product::cross_mapping),
+        )
+        .route(
+            "/api/a007/cross-mapping/resolve",
+            post(handlers::a007_marketplace_product::resolve_cross_mapping_conflict),
+        )
         .layer(middleware::from_fn(
             |req: Request<Body>, next: Next| async move {
                 check_scope("a007_marketplace_product", req, next).await
@@ -489,6 +512,14 @@ fn a012_routes() -> Router {
             "/api/a012/wb-sales",
             get(handlers::a012_wb_sales::list_sales),
         )
+        .route(
+            "/api/a012/wb-sales/export",
+            get(handlers::a012_wb_sales::export_sales),
+        )
+        .route(
+            "/api/a012/wb-sales/compare",
+            get(handlers::a012_wb_sales::compare_sales),
+        )
         .route(
             "/api/a012/wb-sales/:id",
             get(handlers::a012_wb_sales::get_sale_detail),
@@ -501,10 +532,18 @@ fn a012_routes() -> Router {
             "/api/a012/raw/:ref_id",
             get(handlers::a012_wb_sales::get_raw_json),
         )
+        .route(
+            "/api/a012/raw/:ref_id/versions",
+            get(handlers::a012_wb_sales::get_raw_versions),
+        )
         .route(
             "/api/a012/wb-sales/:id/post",
             post(handlers::a012_wb_sales::post_document),
         )
+        .route(
+            "/api/a012/wb-sales/:id/post-preview",
+            get(handlers::a012_wb_sales::post_preview),
+        )
         .route(
             "/api/a012/wb-sales/:id/unpost",
             post(handlers::a012_wb_sales::unpost_document),
@@ -515,11 +554,11 @@ fn a012_routes() -> Router {
         )
         .route(
             "/api/a012/wb-sales/batch-post",
-            post(handlers::a012_wb_sales::batch_post_documents),
+            post(handlers::a012_wb_sales::batch_post),
         )
         .route(
             "/api/a012/wb-sales/batch-unpost",
-            post(handlers::a012_wb_sales::batch_unpost_documents),
+            post(handlers::a012_wb_sales::batch_unpost),
         )
         .route(
             "/api/a012/wb-sales/:id/projections",
@@ -533,6 +572,16 @@ fn a012_routes() -> Router {
             "/api/a012/wb-sales/:id/advert-attribution",
             get(handlers::a012_wb_sales::get_advert_attribution),
         )
+        .route(
+            "/api/a012/wb-sales/:id/finance-links",
+            get(handlers::a012_wb_sales::list_finance_links)
+                .post(handlers::a012_wb_sales::attach_finance_links)
+                .delete(handlers::a012_wb_sales::detach_finance_links),
+        )
+        .route(
+            "/api/a012/wb-sales/:id/finance-links/candidates",
+            get(handlers::a012_wb_sales::finance_link_candidates),
+        )
         .route(
             "/api/a012/wb-sales/:id/refresh-dealer-price",
             post(handlers::a012_wb_sales::refresh_dealer_price),
@@ -582,14 +631,6 @@ fn a013_routes() -> Router {
             "/api/a013/ym-order/post-period",
             post(handlers::a013_ym_order::post_period),
         )
-        .route(
-            "/api/a013/ym-order/batch-post",
-            post(handlers::a013_ym_order::batch_post_documents),
-        )
-        .route(
-            "/api/a013/ym-order/batch-unpost",
-            post(handlers::a013_ym_order::batch_unpost_documents),
-        )
         .layer(middleware::from_fn(
             |req: Request<Body>, next: Next| async move {
                 check_scope("a013_ym_order", req, next).await
@@ -612,6 +653,10 @@ fn a014_routes() -> Router {
             "/api/ozon_transactions/by-posting/:posting_number",
             get(handlers::a014_ozon_transactions::get_by_posting_number),
         )
+        .route(
+            "/api/a014/ozon-transactions/export",
+            get(handlers::a014_ozon_transactions::export),
+        )
         .route(
             "/api/a014/ozon-transactions/:id/post",
             post(handlers::a014_ozon_transactions::post_document),
@@ -637,6 +682,10 @@ fn a015_routes() -> Router {
             "/api/a015/wb-orders",
             get(handlers::a015_wb_orders::list_orders),
         )
+        .route(
+            "/api/a015/wb-orders/export",
+            get(handlers::a015_wb_orders::export_orders),
+        )
         .route(
             "/api/a015/wb-orders/:id",
             get(handlers::a015_wb_orders::get_order_detail),
@@ -649,6 +698,10 @@ fn a015_routes() -> Router {
             "/api/a015/raw/:ref_id",
             get(handlers::a015_wb_orders::get_raw_json),
         )
+        .route(
+            "/api/a015/raw/:ref_id/versions",
+            get(handlers::a015_wb_orders::get_raw_versions),
+        )
         .route(
             "/api/a015/wb-orders/:id/delete",
             post(handlers::a015_wb_orders::delete_order),
@@ -706,14 +759,6 @@ fn a016_routes() -> Router {
             "/api/a016/ym-returns/post-period",
             post(handlers::a016_ym_returns::post_period),
         )
-        .route(
-            "/api/a016/ym-returns/batch-post",
-            post(handlers::a016_ym_returns::batch_post_documents),
-        )
-        .route(
-            "/api/a016/ym-returns/batch-unpost",
-            post(handlers::a016_ym_returns::batch_unpost_documents),
-        )
         .layer(middleware::from_fn(
             |req: Request<Body>, next: Next| async move {
                 check_scope("a016_ym_returns", req, next).await
@@ -1624,6 +1669,22 @@ fn u505_routes() -> Router {
             "/api/u505/match/:session_id/progress",
             get(handlers::usecases::u505_get_progress),
         )
+        .route(
+            "/api/u505/suggestions",
+            get(handlers::usecases::u505_list_suggestions),
+        )
+        .route(
+            "/api/u505/suggestions/decisions",
+            post(handlers::usecases::u505_apply_decisions),
+        )
+        .route(
+            "/api/u505/suggestions/accept-high-confidence",
+            post(handlers::usecases::u505_accept_high_confidence),
+        )
+        .route(
+            "/api/u505/suggestions/accuracy",
+            get(handlers::usecases::u505_accuracy_report),
+        )
         .layer(middleware::from_fn(
             |req: Request<Body>, next: Next| async move {
                 check_scope("u505_match_nomenclature", req, next).await
@@ -1683,6 +1744,10 @@ fn u508_routes() -> Router {
             "/api/u508/repost/aggregate/start",
             post(handlers::usecases::u508_start_aggregate_repost),
         )
+        .route(
+            "/api/u508/repost/period/start",
+            post(handlers::usecases::u508_start_period_repost),
+        )
         .route(
             "/api/u508/repost/funnel/start",
             post(handlers::usecases::u508_start_funnel_rebuild),
@@ -1691,6 +1756,10 @@ fn u508_routes() -> Router {
             "/api/u508/repost/funnel/diagnostics",
             get(handlers::usecases::u508_funnel_diagnostics),
         )
+        .route(
+            "/api/projections/rebuild",
+            post(handlers::usecases::u508_start_projection_rebuild),
+        )
         .route(
             "/api/u508/repost/:session_id/progress",
             get(handlers::usecases::u508_get_progress),
@@ -1702,6 +1771,27 @@ fn u508_routes() -> Router {
         ))
 }
 
+fn u509_routes() -> Router {
+    Router::new()
+        .route(
+            "/api/u509/link/start",
+            post(handlers::usecases::u509_start_linking),
+        )
+        .route(
+            "/api/u509/link/:session_id/progress",
+            get(handlers::usecases::u509_get_progress),
+        )
+        .route(
+            "/api/u509/unmatched",
+            get(handlers::usecases::u509_unmatched_report),
+        )
+        .layer(middleware::from_fn(
+            |req: Request<Body>, next: Next| async move {
+                check_scope("u509_link_ozon_skus", req, next).await
+            },
+        ))
+}
+
 // ============================================================================
 // Projections P900–P912 — each with its own scope
 // ============================================================================
@@ -1732,6 +1822,10 @@ fn p900_routes() -> Router {
             "/api/projections/p900/:registrator_ref",
             get(handlers::p900_mp_sales_register::get_by_registrator),
         )
+        .route(
+            "/api/analytics/sku/:id/sparkline",
+            get(handlers::p900_mp_sales_register::get_sku_sparkline),
+        )
         .layer(middleware::from_fn(
             |req: Request<Body>, next: Next| async move {
                 check_scope("p900_mp_sales_register", req, next).await
@@ -1782,19 +1876,19 @@ fn p902_routes() -> Router {
 }
 
 fn p903_routes() -> Router {
-    Router::new()
+    let scoped_routes = Router::new()
         .route(
             "/api/p903/finance-report",
             get(handlers::p903_wb_finance_report::list_reports),
         )
-        .route(
-            "/api/p903/finance-report/export",
-            get(handlers::p903_wb_finance_report::export_reports),
-        )
         .route(
             "/api/p903/finance-report/search-by-srid",
             get(handlers::p903_wb_finance_report::search_by_srid),
         )
+        .route(
+            "/api/p903/finance-report/search-by-srid/totals",
+            get(handlers::p903_wb_finance_report::totals_by_srid),
+        )
         .route(
             "/api/p903/finance-report/operation-kinds",
             get(handlers::p903_wb_finance_report::list_operation_kinds),
@@ -1815,15 +1909,39 @@ fn p903_routes() -> Router {
             |req: Request<Body>, next: Next| async move {
                 check_scope("p903_wb_finance_report", req, next).await
             },
-        ))
+        ));
+
+    // Выгрузка (фоновая и потоковая) только читает данные — достаточно доступа на чтение.
+    let export_routes = Router::new()
+        .route(
+            "/api/p903/finance-report/export",
+            post(handlers::p903_wb_finance_report::export_reports)
+                .get(handlers::p903_wb_finance_report::export_reports_csv),
+        )
+        .layer(middleware::from_fn(
+            |req: Request<Body>, next: Next| async move {
+                check_scope_read("p903_wb_finance_report", req, next).await
+            },
+        ));
+
+    scoped_routes.merge(export_routes)
 }
 
 fn p904_routes() -> Router {
     Router::new()
         .route("/api/p904/sales-data", get(handlers::p904_sales_data::list))
+        .route(
+            "/api/p904/return-netting/settings",
+            get(handlers::p904_sales_data::get_return_netting_settings)
+                .post(handlers::p904_sales_data::set_return_netting_settings),
+        )
+        .route(
+            "/api/p904/return-netting/unmatched",
+            get(handlers::p904_sales_data::list_unmatched_returns),
+        )
         .layer(middleware::from_fn(
             |req: Request<Body>, next: Next| async move {
-                check_scope_read("p904_sales_data", req, next).await
+                check_scope("p904_sales_data", req, next).await
             },
         ))
 }
@@ -1990,6 +2108,45 @@ fn p915_routes() -> Router {
         ))
 }
 
+fn p918_routes() -> Router {
+    Router::new()
+        .route(
+            "/api/p918/storage-allocation",
+            get(handlers::p918_storage_cost_allocation::list),
+        )
+        .route(
+            "/api/p918/storage-allocation/settings",
+            get(handlers::p918_storage_cost_allocation::get_settings)
+                .post(handlers::p918_storage_cost_allocation::set_settings),
+        )
+        .route(
+            "/api/p918/storage-allocation/rebuild",
+            post(handlers::p918_storage_cost_allocation::rebuild),
+        )
+        .layer(middleware::from_fn(
+            |req: Request<Body>, next: Next| async move {
+                check_scope("p918_storage_cost_allocation", req, next).await
+            },
+        ))
+}
+
+fn p919_routes() -> Router {
+    Router::new()
+        .route(
+            "/api/p919/daily-summary",
+            get(handlers::p919_daily_sales_summary::list),
+        )
+        .route(
+            "/api/p919/daily-summary/rebuild",
+            post(handlers::p919_daily_sales_summary::rebuild),
+        )
+        .layer(middleware::from_fn(
+            |req: Request<Body>, next: Next| async move {
+                check_scope("p919_daily_sales_summary", req, next).await
+            },
+        ))
+}
+
 // ============================================================================
 // Indicators
 // ============================================================================
@@ -2016,6 +2173,34 @@ fn dashboard_routes() -> Router {
             "/api/dashboards/wb-advert-report",
             get(handlers::dashboards::wb_advert_report),
         )
+        .route(
+            "/api/dashboards/wb-supply-acceptance",
+            get(handlers::dashboards::wb_supply_acceptance),
+        )
+        .route(
+            "/api/dashboards/pnl-statement",
+            get(handlers::dashboards::pnl_statement),
+        )
+        .route(
+            "/api/dashboards/margin-scenario",
+            post(handlers::dashboards::margin_scenario),
+        )
+        .route(
+            "/api/dashboards/sku-launch-cohorts",
+            get(handlers::dashboards::sku_launch_cohorts),
+        )
+        .route(
+            "/api/dashboards/demand-forecast",
+            get(handlers::dashboards::demand_forecast),
+        )
+        .route(
+            "/api/dashboards/import-coverage",
+            get(handlers::dashboards::import_coverage),
+        )
+        .route(
+            "/api/dashboards/sku-pnl",
+            get(handlers::dashboards::sku_pnl),
+        )
         .route(
             "/api/dashboards/wb-sales-funnel",
             get(handlers::dashboards::wb_sales_funnel),
@@ -2385,6 +2570,10 @@ fn ext_routes() -> Router {
             "/api/ext/v1/wb-finance-report",
             get(handlers::ext_bi_wb_finance::list_finance_report),
         )
+        .route(
+            "/api/ext/v1/changes",
+            get(handlers::ext_cdc_changes::list_changes),
+        )
         .layer(middleware::from_fn(
             crate::system::auth::middleware::check_api_key,
         ))
@@ -2427,9 +2616,15 @@ fn quality_routes() -> Router {
             "/api/quality/checks/:id/repost",
             post(handlers::quality::bulk_repost),
         )
+        .route(
+            "/api/quality/checks/:id/acknowledge",
+            post(handlers::quality::acknowledge_violations),
+        )
         .route(
             "/api/quality/checks/:id/cleanup",
-            post(handlers::quality::cleanup_orphans),
+            post(handlers::quality::cleanup_orphans).layer(middleware::from_fn(
+                crate::system::environment::require_confirmation,
+            )),
         )
         .layer(middleware::from_fn(
             |req: Request<Body>, next: Next| async move {
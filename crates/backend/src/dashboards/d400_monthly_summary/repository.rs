@@ -3,6 +3,7 @@ use sea_orm::{FromQueryResult, Statement};
 use serde::{Deserialize, Serialize};
 
 use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
 
 /// Raw aggregation result from SQL query
 #[derive(Debug, Clone, Serialize, Deserialize, FromQueryResult)]
@@ -35,7 +36,8 @@ pub async fn get_revenue_by_marketplace_and_org(
 ) -> Result<Vec<RevenueAggregation>> {
     let db = get_connection();
 
-    let sql = r#"
+    let sql = format!(
+        r#"
         SELECT 
             CASE mp.marketplace_type
                 WHEN 'mp-wb' THEN 'WB'
@@ -47,18 +49,20 @@ pub async fn get_revenue_by_marketplace_and_org(
             END AS marketplace_code,
             org.description AS organization_name,
             COALESCE(SUM(p904.customer_in), 0) AS total_revenue
-        FROM p904_sales_data p904
+        FROM {source} p904
         LEFT JOIN a006_connection_mp conn ON p904.connection_mp_ref = conn.id
         LEFT JOIN a002_organization org ON conn.organization_ref = org.id
         LEFT JOIN a005_marketplace mp ON conn.marketplace = mp.id
         WHERE substr(p904.date, 1, 10) >= ? AND substr(p904.date, 1, 10) <= ?
         GROUP BY mp.marketplace_type, conn.organization_ref, org.description
         ORDER BY marketplace_code, organization_name
-    "#;
+    "#,
+        source = projection_archive::source("p904_sales_data", Some(date_from))
+    );
 
     let stmt = Statement::from_sql_and_values(
         sea_orm::DatabaseBackend::Sqlite,
-        sql,
+        &sql,
         [date_from.into(), date_to.into()],
     );
 
@@ -74,7 +78,8 @@ pub async fn get_returns_by_marketplace_and_org(
 ) -> Result<Vec<ReturnsAggregation>> {
     let db = get_connection();
 
-    let sql = r#"
+    let sql = format!(
+        r#"
         SELECT 
             CASE mp.marketplace_type
                 WHEN 'mp-wb' THEN 'WB'
@@ -86,18 +91,20 @@ pub async fn get_returns_by_marketplace_and_org(
             END AS marketplace_code,
             org.description AS organization_name,
             COALESCE(SUM(p904.customer_out), 0) AS total_returns
-        FROM p904_sales_data p904
+        FROM {source} p904
         LEFT JOIN a006_connection_mp conn ON p904.connection_mp_ref = conn.id
         LEFT JOIN a002_organization org ON conn.organization_ref = org.id
         LEFT JOIN a005_marketplace mp ON conn.marketplace = mp.id
         WHERE substr(p904.date, 1, 10) >= ? AND substr(p904.date, 1, 10) <= ?
         GROUP BY mp.marketplace_type, conn.organization_ref, org.description
         ORDER BY marketplace_code, organization_name
-    "#;
+    "#,
+        source = projection_archive::source("p904_sales_data", Some(date_from))
+    );
 
     let stmt = Statement::from_sql_and_values(
         sea_orm::DatabaseBackend::Sqlite,
-        sql,
+        &sql,
         [date_from.into(), date_to.into()],
     );
 
@@ -113,7 +120,8 @@ pub async fn get_cost_by_marketplace_and_org(
 ) -> Result<Vec<CostAggregation>> {
     let db = get_connection();
 
-    let sql = r#"
+    let sql = format!(
+        r#"
         SELECT 
             CASE mp.marketplace_type
                 WHEN 'mp-wb' THEN 'WB'
@@ -125,18 +133,20 @@ pub async fn get_cost_by_marketplace_and_org(
             END AS marketplace_code,
             org.description AS organization_name,
             -CAST(COALESCE(SUM(p904.cost), 0) AS REAL) AS total_cost
-        FROM p904_sales_data p904
+        FROM {source} p904
         LEFT JOIN a006_connection_mp conn ON p904.connection_mp_ref = conn.id
         LEFT JOIN a002_organization org ON conn.organization_ref = org.id
         LEFT JOIN a005_marketplace mp ON conn.marketplace = mp.id
         WHERE substr(p904.date, 1, 10) >= ? AND substr(p904.date, 1, 10) <= ?
         GROUP BY mp.marketplace_type, conn.organization_ref, org.description
         ORDER BY marketplace_code, organization_name
-    "#;
+    "#,
+        source = projection_archive::source("p904_sales_data", Some(date_from))
+    );
 
     let stmt = Statement::from_sql_and_values(
         sea_orm::DatabaseBackend::Sqlite,
-        sql,
+        &sql,
         [date_from.into(), date_to.into()],
     );
 
@@ -149,19 +159,22 @@ pub async fn get_cost_by_marketplace_and_org(
 pub async fn get_available_periods() -> Result<Vec<String>> {
     let db = get_connection();
 
-    let sql = r#"
+    let sql = format!(
+        r#"
         SELECT DISTINCT SUBSTR(p904.date, 1, 7) AS period
-        FROM p904_sales_data p904
+        FROM {source} p904
         WHERE p904.date IS NOT NULL AND p904.date != ''
         ORDER BY period DESC
-    "#;
+    "#,
+        source = projection_archive::source("p904_sales_data", None)
+    );
 
     #[derive(Debug, FromQueryResult)]
     struct PeriodRow {
         period: Option<String>,
     }
 
-    let stmt = Statement::from_sql_and_values(sea_orm::DatabaseBackend::Sqlite, sql, []);
+    let stmt = Statement::from_sql_and_values(sea_orm::DatabaseBackend::Sqlite, &sql, []);
     let results = PeriodRow::find_by_statement(stmt).all(db).await?;
 
     Ok(results.into_iter().filter_map(|r| r.period).collect())
@@ -199,17 +212,20 @@ pub async fn get_active_marketplaces() -> Result<Vec<String>> {
 pub async fn get_organizations_with_sales(date_from: &str, date_to: &str) -> Result<Vec<String>> {
     let db = get_connection();
 
-    let sql = r#"
+    let sql = format!(
+        r#"
         SELECT DISTINCT 
             org.description AS organization_name
-        FROM p904_sales_data p904
+        FROM {source} p904
         JOIN a006_connection_mp conn ON p904.connection_mp_ref = conn.id
         JOIN a002_organization org ON conn.organization_ref = org.id
         WHERE substr(p904.date, 1, 10) >= ? AND substr(p904.date, 1, 10) <= ?
             AND conn.organization_ref IS NOT NULL
             AND conn.organization_ref != ''
         ORDER BY org.description
-    "#;
+    "#,
+        source = projection_archive::source("p904_sales_data", Some(date_from))
+    );
 
     #[derive(Debug, FromQueryResult)]
     struct OrgInfo {
@@ -218,7 +234,7 @@ pub async fn get_organizations_with_sales(date_from: &str, date_to: &str) -> Res
 
     let stmt = Statement::from_sql_and_values(
         sea_orm::DatabaseBackend::Sqlite,
-        sql,
+        &sql,
         [date_from.into(), date_to.into()],
     );
 
@@ -2,9 +2,11 @@ use anyhow::Result;
 use contracts::dashboards::d400_monthly_summary::{
     DrilldownFilter, IndicatorRow, MonthlySummaryRequest, MonthlySummaryResponse,
 };
+use contracts::system::data_freshness::FreshnessDataset;
 use std::collections::HashMap;
 
 use super::repository;
+use crate::system::data_freshness;
 
 /// Get monthly summary data
 pub async fn get_monthly_summary(request: MonthlySummaryRequest) -> Result<MonthlySummaryResponse> {
@@ -97,10 +99,18 @@ pub async fn get_monthly_summary(request: MonthlySummaryRequest) -> Result<Month
     );
     rows.extend(result_rows);
 
+    let freshness = data_freshness::for_datasets(&[
+        FreshnessDataset::WbSales,
+        FreshnessDataset::Ozon,
+        FreshnessDataset::Yandex,
+    ])
+    .await;
+
     Ok(MonthlySummaryResponse {
         period,
         rows,
         marketplaces,
+        freshness,
     })
 }
 
@@ -0,0 +1,4 @@
+//! D414 — покрытие импорта: сводки маркетплейсов против загруженных документов.
+
+pub mod repository;
+pub mod service;
@@ -0,0 +1,230 @@
+use anyhow::Result;
+use chrono::{Duration, NaiveDate};
+use contracts::dashboards::d414_import_coverage::CoverageSource;
+use sea_orm::{ConnectionTrait, Statement, TransactionTrait, Value};
+
+use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+
+/// Сдвиг `operation_date` Ozon (МСК) к дню UTC, которым ограничен запрос сводки.
+const OZON_OPERATION_DATE_SHIFT: &str = "-3 hours";
+
+/// Сводка маркетплейса за день: сколько документов и на какую сумму он заявил.
+#[derive(Debug, Clone)]
+pub struct ImportClaim {
+    pub connection_mp_ref: String,
+    pub source: CoverageSource,
+    pub day: String,
+    pub claimed_count: i64,
+    pub claimed_amount: Option<f64>,
+    pub checked_at: String,
+}
+
+/// Что лежит в агрегате за день.
+#[derive(Debug, Clone)]
+pub struct ImportedDay {
+    pub connection_mp_ref: String,
+    pub day: String,
+    pub doc_count: i64,
+    pub amount: f64,
+}
+
+/// Кабинет маркетплейса с кодом типа (`mp-wb`, `mp-ozon`, …).
+#[derive(Debug, Clone)]
+pub struct CoverageConnection {
+    pub id: String,
+    pub description: String,
+    pub marketplace_code: String,
+}
+
+fn next_day(day: &str) -> String {
+    NaiveDate::parse_from_str(day, "%Y-%m-%d")
+        .map(|date| (date + Duration::days(1)).format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|_| day.to_string())
+}
+
+/// Используемые кабинеты; `only` — один кабинет, `organization_ref` — кабинеты организации.
+pub async fn list_connections(
+    only: Option<&str>,
+    organization_ref: Option<&str>,
+) -> Result<Vec<CoverageConnection>> {
+    let db = get_connection();
+    let mut sql =
+        "SELECT c.id, c.description, COALESCE(mp.marketplace_type, '') AS marketplace_code \
+         FROM a006_connection_mp c \
+         LEFT JOIN a005_marketplace mp ON mp.id = c.marketplace \
+         WHERE c.is_deleted = 0 AND c.is_used = 1"
+            .to_string();
+    let mut values: Vec<Value> = Vec::new();
+    if let Some(id) = only {
+        sql.push_str(" AND c.id = ?");
+        values.push(id.into());
+    }
+    if let Some(organization) = organization_ref {
+        sql.push_str(
+            " AND LOWER(TRIM(REPLACE(COALESCE(c.organization_ref, ''), '\"', ''))) \
+             = LOWER(TRIM(REPLACE(?, '\"', '')))",
+        );
+        values.push(organization.into());
+    }
+    sql.push_str(" ORDER BY c.description");
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(CoverageConnection {
+                id: row.try_get("", "id").ok()?,
+                description: row.try_get("", "description").unwrap_or_default(),
+                marketplace_code: row.try_get("", "marketplace_code").unwrap_or_default(),
+            })
+        })
+        .collect())
+}
+
+/// Сохраняет сводки; повторная проверка дня перезаписывает прежнюю.
+pub async fn upsert_claims(claims: &[ImportClaim]) -> Result<()> {
+    let db = get_connection();
+    let txn = db.begin().await?;
+    for claim in claims {
+        txn.execute(Statement::from_sql_and_values(
+            txn.get_database_backend(),
+            "INSERT INTO d414_import_claims \
+                 (connection_mp_ref, source, day, claimed_count, claimed_amount, checked_at) \
+             VALUES (?, ?, ?, ?, ?, ?) \
+             ON CONFLICT(connection_mp_ref, source, day) DO UPDATE SET \
+                 claimed_count = excluded.claimed_count, \
+                 claimed_amount = excluded.claimed_amount, \
+                 checked_at = excluded.checked_at",
+            [
+                claim.connection_mp_ref.clone().into(),
+                claim.source.code().into(),
+                claim.day.clone().into(),
+                claim.claimed_count.into(),
+                claim.claimed_amount.into(),
+                claim.checked_at.clone().into(),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+pub async fn list_claims(
+    date_from: &str,
+    date_to: &str,
+    connection_mp_ref: Option<&str>,
+) -> Result<Vec<ImportClaim>> {
+    let db = get_connection();
+    let mut sql =
+        "SELECT connection_mp_ref, source, day, claimed_count, claimed_amount, checked_at \
+         FROM d414_import_claims WHERE day >= ? AND day <= ?"
+            .to_string();
+    let mut values: Vec<Value> = vec![date_from.into(), date_to.into()];
+    if let Some(id) = connection_mp_ref {
+        sql.push_str(" AND connection_mp_ref = ?");
+        values.push(id.into());
+    }
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let source: String = row.try_get("", "source").ok()?;
+            Some(ImportClaim {
+                connection_mp_ref: row.try_get("", "connection_mp_ref").ok()?,
+                source: CoverageSource::from_code(&source)?,
+                day: row.try_get("", "day").ok()?,
+                claimed_count: row.try_get("", "claimed_count").unwrap_or_default(),
+                claimed_amount: row
+                    .try_get::<Option<f64>>("", "claimed_amount")
+                    .ok()
+                    .flatten(),
+                checked_at: row.try_get("", "checked_at").unwrap_or_default(),
+            })
+        })
+        .collect())
+}
+
+/// Импортированное по дням для источника.
+///
+/// a012: день продажи по времени WB (`sale_date` уже в МСК), сумма `finished_price`.
+/// a014: день UTC (так ограничен запрос сводки Ozon), сумма `amount` операции.
+pub async fn imported_by_day(
+    source: CoverageSource,
+    date_from: &str,
+    date_to: &str,
+    connection_mp_ref: Option<&str>,
+) -> Result<Vec<ImportedDay>> {
+    let (mut sql, mut values): (String, Vec<Value>) = match source {
+        CoverageSource::WbSales => (
+            format!(
+                "SELECT connection_id AS connection_mp_ref, substr(sale_date, 1, 10) AS day, \
+                        COUNT(*) AS doc_count, COALESCE(SUM(finished_price), 0) AS amount \
+                 FROM {} \
+                 WHERE is_deleted = 0 AND sale_date >= ? AND sale_date < ?",
+                projection_archive::source("a012_wb_sales", Some(date_from))
+            ),
+            vec![date_from.into(), next_day(date_to).into()],
+        ),
+        CoverageSource::OzonTransactions => (
+            format!(
+                "SELECT json_extract(header_json, '$.connection_id') AS connection_mp_ref, \
+                        date(datetime(json_extract(header_json, '$.operation_date'), '{shift}')) AS day, \
+                        COUNT(*) AS doc_count, \
+                        COALESCE(SUM(json_extract(header_json, '$.amount')), 0) AS amount \
+                 FROM a014_ozon_transactions \
+                 WHERE is_deleted = 0 \
+                   AND datetime(json_extract(header_json, '$.operation_date'), '{shift}') >= ? \
+                   AND datetime(json_extract(header_json, '$.operation_date'), '{shift}') < ?",
+                shift = OZON_OPERATION_DATE_SHIFT
+            ),
+            vec![
+                format!("{date_from} 00:00:00").into(),
+                format!("{} 00:00:00", next_day(date_to)).into(),
+            ],
+        ),
+    };
+    let connection_column = match source {
+        CoverageSource::WbSales => "connection_id",
+        CoverageSource::OzonTransactions => "json_extract(header_json, '$.connection_id')",
+    };
+    if let Some(id) = connection_mp_ref {
+        sql.push_str(&format!(" AND {connection_column} = ?"));
+        values.push(id.into());
+    }
+    sql.push_str(" GROUP BY connection_mp_ref, day");
+
+    let db = get_connection();
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(ImportedDay {
+                connection_mp_ref: row
+                    .try_get::<Option<String>>("", "connection_mp_ref")
+                    .ok()??,
+                day: row.try_get::<Option<String>>("", "day").ok()??,
+                doc_count: row.try_get("", "doc_count").unwrap_or_default(),
+                amount: row.try_get("", "amount").unwrap_or_default(),
+            })
+        })
+        .collect())
+}
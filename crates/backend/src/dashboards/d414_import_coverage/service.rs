@@ -0,0 +1,332 @@
+use anyhow::Result;
+use chrono::{Duration, NaiveDate};
+use contracts::dashboards::d414_import_coverage::{
+    classify, CoverageSource, ImportCoverageRequest, ImportCoverageResponse, ImportCoverageRow,
+};
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::common::AggregateId;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::repository::{self, CoverageConnection, ImportClaim, ImportedDay};
+use crate::shared::marketplaces::wildberries::datetime::{parse_wb_datetime, wb_business_date_str};
+use crate::usecases::u502_import_from_ozon::ozon_api_client::OzonApiClient;
+use crate::usecases::u504_import_from_wildberries::wildberries_api_client::{
+    WbSaleRow, WildberriesApiClient,
+};
+
+/// Источник сверки для кабинета по коду его маркетплейса.
+pub fn source_for(connection: &CoverageConnection) -> Option<CoverageSource> {
+    CoverageSource::all()
+        .into_iter()
+        .find(|source| source.marketplace_code() == connection.marketplace_code)
+}
+
+/// Запрашивает у маркетплейса сводку по дням `date_from..=date_to`.
+pub async fn fetch_claims(
+    connection: &ConnectionMP,
+    source: CoverageSource,
+    date_from: NaiveDate,
+    date_to: NaiveDate,
+    checked_at: &str,
+) -> Result<Vec<ImportClaim>> {
+    let connection_mp_ref = connection.base.id.as_string();
+    let by_day: BTreeMap<String, (i64, Option<f64>)> = match source {
+        CoverageSource::WbSales => {
+            // Отчёт продаж отдаёт всё, что менялось с dateFrom: режем по дню продажи.
+            let rows = WildberriesApiClient::new()
+                .fetch_sales(connection, date_from, date_to)
+                .await?;
+            let rows: Vec<WbSaleRow> = rows.into_iter().map(|(row, _)| row).collect();
+            wb_sales_by_day(&rows, date_from, date_to)
+        }
+        CoverageSource::OzonTransactions => {
+            let client = OzonApiClient::new();
+            let mut by_day = BTreeMap::new();
+            let mut day = date_from;
+            while day <= date_to {
+                // Страница из одной строки: нужен только row_count.
+                let list = client
+                    .fetch_transactions_list(connection, day, day, 1, 1, |_| {})
+                    .await?;
+                let totals = client
+                    .fetch_transaction_totals(connection, day, day)
+                    .await?;
+                by_day.insert(
+                    day.format("%Y-%m-%d").to_string(),
+                    (list.result.row_count as i64, Some(totals.net_amount())),
+                );
+                day += Duration::days(1);
+            }
+            by_day
+        }
+    };
+
+    Ok(by_day
+        .into_iter()
+        .map(|(day, (claimed_count, claimed_amount))| ImportClaim {
+            connection_mp_ref: connection_mp_ref.clone(),
+            source,
+            day,
+            claimed_count,
+            claimed_amount,
+            checked_at: checked_at.to_string(),
+        })
+        .collect())
+}
+
+/// Продажи и возвраты WB по дню продажи (время WB); каждый `saleID` считается один раз,
+/// как и в a012. Дни периода без строк попадают в результат с нулями.
+fn wb_sales_by_day(
+    rows: &[WbSaleRow],
+    date_from: NaiveDate,
+    date_to: NaiveDate,
+) -> BTreeMap<String, (i64, Option<f64>)> {
+    let mut by_day: BTreeMap<String, (i64, Option<f64>)> = BTreeMap::new();
+    let mut day = date_from;
+    while day <= date_to {
+        by_day.insert(day.format("%Y-%m-%d").to_string(), (0, Some(0.0)));
+        day += Duration::days(1);
+    }
+    let mut seen = BTreeSet::new();
+    for row in rows {
+        let Some(key) = row.sale_id.clone().or_else(|| row.srid.clone()) else {
+            continue;
+        };
+        let Some(sale_day) = row
+            .sale_dt
+            .as_deref()
+            .and_then(parse_wb_datetime)
+            .map(|dt| wb_business_date_str(&dt))
+        else {
+            continue;
+        };
+        let Some(entry) = by_day.get_mut(&sale_day) else {
+            continue;
+        };
+        if !seen.insert(key) {
+            continue;
+        }
+        entry.0 += 1;
+        entry.1 = Some(entry.1.unwrap_or_default() + row.finished_price.unwrap_or_default());
+    }
+    by_day
+}
+
+/// Строки отчёта: объединение сводок и импортированного по (кабинет, источник, день).
+fn merge_rows(
+    claims: &[ImportClaim],
+    imported: &[(CoverageSource, ImportedDay)],
+    connection_names: &HashMap<String, String>,
+) -> Vec<ImportCoverageRow> {
+    type Key = (String, String, CoverageSource);
+    let mut claim_by_key: HashMap<Key, &ImportClaim> = HashMap::new();
+    for claim in claims {
+        claim_by_key.insert(
+            (
+                claim.day.clone(),
+                claim.connection_mp_ref.clone(),
+                claim.source,
+            ),
+            claim,
+        );
+    }
+    let mut imported_by_key: HashMap<Key, &ImportedDay> = HashMap::new();
+    for (source, day) in imported {
+        imported_by_key.insert(
+            (day.day.clone(), day.connection_mp_ref.clone(), *source),
+            day,
+        );
+    }
+
+    let keys: BTreeSet<(String, String, &'static str)> = claim_by_key
+        .keys()
+        .chain(imported_by_key.keys())
+        .map(|(day, connection, source)| (day.clone(), connection.clone(), source.code()))
+        .collect();
+
+    let mut rows: Vec<ImportCoverageRow> = keys
+        .into_iter()
+        .filter_map(|(day, connection_mp_ref, source_code)| {
+            let source = CoverageSource::from_code(source_code)?;
+            let key = (day.clone(), connection_mp_ref.clone(), source);
+            let claim = claim_by_key.get(&key);
+            let imported = imported_by_key.get(&key);
+            let claimed_count = claim.map(|c| c.claimed_count);
+            let claimed_amount = claim.and_then(|c| c.claimed_amount);
+            let imported_count = imported.map(|i| i.doc_count).unwrap_or_default();
+            let imported_amount = imported.map(|i| i.amount).unwrap_or_default();
+            Some(ImportCoverageRow {
+                status: classify(
+                    claimed_count,
+                    claimed_amount,
+                    imported_count,
+                    imported_amount,
+                ),
+                connection_name: connection_names
+                    .get(&connection_mp_ref)
+                    .cloned()
+                    .unwrap_or_else(|| connection_mp_ref.clone()),
+                checked_at: claim.map(|c| c.checked_at.clone()),
+                day,
+                connection_mp_ref,
+                source,
+                claimed_count,
+                claimed_amount,
+                imported_count,
+                imported_amount,
+            })
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.day
+            .cmp(&a.day)
+            .then_with(|| a.connection_name.cmp(&b.connection_name))
+            .then_with(|| a.source.code().cmp(b.source.code()))
+    });
+    rows
+}
+
+/// Отчёт D414 за период. `organization_ref` оставляет кабинеты одной организации:
+/// сводки и агрегаты чужих кабинетов отбрасываются вместе с ними.
+pub async fn build_report(
+    filters: ImportCoverageRequest,
+    date_from: NaiveDate,
+    date_to: NaiveDate,
+    organization_ref: Option<&str>,
+) -> Result<ImportCoverageResponse> {
+    let from = date_from.format("%Y-%m-%d").to_string();
+    let to = date_to.format("%Y-%m-%d").to_string();
+    let connection_filter = filters
+        .connection_mp_ref
+        .as_deref()
+        .filter(|value| !value.is_empty());
+
+    let connections = repository::list_connections(connection_filter, organization_ref).await?;
+    let connection_names: HashMap<String, String> = connections
+        .iter()
+        .map(|c| (c.id.clone(), c.description.clone()))
+        .collect();
+    let sources: BTreeSet<&'static str> = connections
+        .iter()
+        .filter_map(source_for)
+        .map(|source| source.code())
+        .collect();
+
+    let mut claims = repository::list_claims(&from, &to, connection_filter).await?;
+    claims.retain(|claim| connection_names.contains_key(&claim.connection_mp_ref));
+    let mut imported = Vec::new();
+    for source in sources.into_iter().filter_map(CoverageSource::from_code) {
+        for day in repository::imported_by_day(source, &from, &to, connection_filter).await? {
+            // Кабинеты, которые больше не используются, в отчёт не попадают.
+            if connection_names.contains_key(&day.connection_mp_ref) {
+                imported.push((source, day));
+            }
+        }
+    }
+
+    let mut rows = merge_rows(&claims, &imported, &connection_names);
+    let problem_rows = rows.iter().filter(|row| row.status.is_problem()).count();
+    let gap_days = rows
+        .iter()
+        .filter(|row| row.status.is_gap())
+        .map(|row| row.day.as_str())
+        .collect::<BTreeSet<_>>()
+        .len();
+    let last_checked_at = claims.iter().map(|c| c.checked_at.clone()).max();
+    if filters.problems_only.unwrap_or(false) {
+        rows.retain(|row| row.status.is_problem());
+    }
+
+    Ok(ImportCoverageResponse {
+        filters,
+        rows,
+        problem_rows,
+        gap_days,
+        last_checked_at,
+    })
+}
+
+/// Текст уведомления: первые `limit` расхождений.
+pub fn notification_text(rows: &[ImportCoverageRow], limit: usize) -> String {
+    let mut lines: Vec<String> = rows
+        .iter()
+        .take(limit)
+        .map(|row| {
+            format!(
+                "{} · {} · {}: {} (заявлено {}, загружено {})",
+                row.day,
+                row.connection_name,
+                row.source.label(),
+                row.status.label(),
+                row.claimed_count.unwrap_or_default(),
+                row.imported_count
+            )
+        })
+        .collect();
+    if rows.len() > limit {
+        lines.push(format!("… и ещё {}", rows.len() - limit));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::dashboards::d414_import_coverage::CoverageStatus;
+
+    fn sale(id: &str, date: &str, price: f64) -> WbSaleRow {
+        serde_json::from_value(serde_json::json!({
+            "saleID": id,
+            "date": date,
+            "finishedPrice": price,
+        }))
+        .unwrap()
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn wb_sales_are_counted_once_per_sale_day() {
+        let rows = vec![
+            sale("S1", "2026-03-01T10:00:00", 100.0),
+            sale("S1", "2026-03-01T10:00:00", 100.0),
+            sale("R2", "2026-03-02T23:30:00", -50.0),
+            sale("S3", "2026-02-28T12:00:00", 70.0),
+        ];
+        let by_day = wb_sales_by_day(&rows, date("2026-03-01"), date("2026-03-03"));
+        assert_eq!(by_day["2026-03-01"], (1, Some(100.0)));
+        assert_eq!(by_day["2026-03-02"], (1, Some(-50.0)));
+        assert_eq!(by_day["2026-03-03"], (0, Some(0.0)));
+        assert!(!by_day.contains_key("2026-02-28"));
+    }
+
+    #[test]
+    fn merge_marks_missing_and_unchecked_days() {
+        let claims = vec![ImportClaim {
+            connection_mp_ref: "c1".to_string(),
+            source: CoverageSource::WbSales,
+            day: "2026-03-01".to_string(),
+            claimed_count: 4,
+            claimed_amount: Some(400.0),
+            checked_at: "2026-03-02T06:00:00Z".to_string(),
+        }];
+        let imported = vec![(
+            CoverageSource::WbSales,
+            ImportedDay {
+                connection_mp_ref: "c1".to_string(),
+                day: "2026-03-02".to_string(),
+                doc_count: 3,
+                amount: 300.0,
+            },
+        )];
+        let names = HashMap::from([("c1".to_string(), "WB основной".to_string())]);
+        let rows = merge_rows(&claims, &imported, &names);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].day, "2026-03-02");
+        assert_eq!(rows[0].status, CoverageStatus::NotChecked);
+        assert_eq!(rows[1].status, CoverageStatus::Missing);
+        assert_eq!(rows[1].connection_name, "WB основной");
+    }
+}
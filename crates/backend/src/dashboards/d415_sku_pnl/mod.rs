@@ -0,0 +1,4 @@
+//! D415 — P&L по SKU из p904 с сравнением с предыдущим периодом.
+
+pub mod repository;
+pub mod service;
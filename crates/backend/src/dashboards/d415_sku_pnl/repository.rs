@@ -0,0 +1,120 @@
+use anyhow::Result;
+use contracts::dashboards::d415_sku_pnl::SkuPnlMetrics;
+use sea_orm::{ConnectionTrait, Statement, Value};
+use std::collections::HashMap;
+
+use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+
+/// Лимит параметров одного запроса SQLite с запасом.
+const IN_CHUNK: usize = 500;
+
+/// Продажи SKU за день из p904.
+#[derive(Debug, Clone)]
+pub struct SkuDay {
+    pub sku_key: String,
+    pub nomenclature_ref: String,
+    pub article: String,
+    /// `YYYY-MM-DD`.
+    pub day: String,
+    pub metrics: SkuPnlMetrics,
+}
+
+/// Суммы p904 по SKU и дню за `date_from..=date_to`; старые периоды читаются вместе с архивом.
+pub async fn sku_days(
+    date_from: &str,
+    date_to: &str,
+    connection_mp_ref: Option<&str>,
+    organization_ref: Option<&str>,
+) -> Result<Vec<SkuDay>> {
+    let mut sql = format!(
+        "SELECT CASE WHEN p904.nomenclature_ref <> '' THEN p904.nomenclature_ref \
+                     ELSE 'article:' || p904.article END AS sku_key, \
+                MAX(p904.nomenclature_ref) AS nomenclature_ref, \
+                MAX(p904.article) AS article, \
+                substr(p904.date, 1, 10) AS day, \
+                COALESCE(SUM(p904.customer_in + p904.customer_out), 0) AS revenue, \
+                COALESCE(SUM(p904.commission_out), 0) AS commission, \
+                COALESCE(SUM(p904.acquiring_out), 0) AS acquiring, \
+                COALESCE(SUM(p904.coinvest_in), 0) AS coinvest, \
+                COALESCE(SUM(-p904.seller_out), 0) AS payout \
+         FROM {source} p904 \
+         WHERE substr(p904.date, 1, 10) >= ? AND substr(p904.date, 1, 10) <= ?",
+        source = projection_archive::source("p904_sales_data", Some(date_from))
+    );
+    let mut values: Vec<Value> = vec![date_from.into(), date_to.into()];
+    if let Some(id) = connection_mp_ref {
+        sql.push_str(" AND p904.connection_mp_ref = ?");
+        values.push(id.into());
+    }
+    // Организации в строке нет — она берётся из кабинета маркетплейса
+    if let Some(organization) = organization_ref {
+        sql.push_str(
+            " AND p904.connection_mp_ref IN (SELECT id FROM a006_connection_mp \
+             WHERE LOWER(TRIM(REPLACE(COALESCE(organization_ref, ''), '\"', ''))) = LOWER(TRIM(REPLACE(?, '\"', ''))))",
+        );
+        values.push(organization.into());
+    }
+    sql.push_str(" GROUP BY sku_key, day");
+
+    let db = get_connection();
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(SkuDay {
+                sku_key: row.try_get("", "sku_key").ok()?,
+                nomenclature_ref: row.try_get("", "nomenclature_ref").unwrap_or_default(),
+                article: row.try_get("", "article").unwrap_or_default(),
+                day: row.try_get("", "day").ok()?,
+                metrics: SkuPnlMetrics {
+                    revenue: row.try_get("", "revenue").unwrap_or_default(),
+                    commission: row.try_get("", "commission").unwrap_or_default(),
+                    acquiring: row.try_get("", "acquiring").unwrap_or_default(),
+                    coinvest: row.try_get("", "coinvest").unwrap_or_default(),
+                    payout: row.try_get("", "payout").unwrap_or_default(),
+                },
+            })
+        })
+        .collect())
+}
+
+/// Артикул и наименование номенклатуры (a004) по id.
+pub async fn nomenclature_names(ids: &[String]) -> Result<HashMap<String, (String, String)>> {
+    let db = get_connection();
+    let mut names = HashMap::new();
+    for chunk in ids.chunks(IN_CHUNK) {
+        let placeholders = chunk.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
+        let sql = format!(
+            "SELECT id, COALESCE(article, '') AS article, COALESCE(description, '') AS description \
+             FROM a004_nomenclature WHERE id IN ({placeholders})"
+        );
+        let values: Vec<Value> = chunk.iter().map(|id| id.clone().into()).collect();
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                db.get_database_backend(),
+                &sql,
+                values,
+            ))
+            .await?;
+        for row in rows {
+            let Ok(id) = row.try_get::<String>("", "id") else {
+                continue;
+            };
+            names.insert(
+                id,
+                (
+                    row.try_get("", "article").unwrap_or_default(),
+                    row.try_get("", "description").unwrap_or_default(),
+                ),
+            );
+        }
+    }
+    Ok(names)
+}
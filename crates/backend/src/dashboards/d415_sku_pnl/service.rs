@@ -0,0 +1,148 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use contracts::dashboards::d415_sku_pnl::{
+    previous_period, trend_bucket, trend_bucket_count, SkuPnlMetrics, SkuPnlRequest,
+    SkuPnlResponse, SkuPnlRow,
+};
+use std::collections::HashMap;
+
+use super::repository::{self, SkuDay};
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Раскладывает дни p904 на текущий и предыдущий период и тренд по отрезкам.
+fn fold_rows(days: Vec<SkuDay>, date_from: NaiveDate, date_to: NaiveDate) -> Vec<SkuPnlRow> {
+    let buckets = trend_bucket_count(date_from, date_to);
+    let mut rows: HashMap<String, SkuPnlRow> = HashMap::new();
+    for day in days {
+        let Ok(date) = NaiveDate::parse_from_str(&day.day, "%Y-%m-%d") else {
+            continue;
+        };
+        let row = rows
+            .entry(day.sku_key.clone())
+            .or_insert_with(|| SkuPnlRow {
+                sku_key: day.sku_key.clone(),
+                nomenclature_ref: Some(day.nomenclature_ref.clone()).filter(|id| !id.is_empty()),
+                article: day.article.clone(),
+                description: String::new(),
+                current: SkuPnlMetrics::default(),
+                previous: SkuPnlMetrics::default(),
+                trend: vec![0.0; buckets],
+            });
+        match trend_bucket(date, date_from, date_to) {
+            Some(bucket) => {
+                row.current.add(&day.metrics);
+                row.trend[bucket] += day.metrics.payout;
+            }
+            None if date < date_from => row.previous.add(&day.metrics),
+            None => {}
+        }
+    }
+    let mut rows: Vec<SkuPnlRow> = rows.into_values().collect();
+    rows.sort_by(|a, b| {
+        b.current
+            .payout
+            .total_cmp(&a.current.payout)
+            .then_with(|| a.article.cmp(&b.article))
+    });
+    rows
+}
+
+/// P&L по SKU за период и предыдущий период той же длины.
+pub async fn build_report(
+    filters: SkuPnlRequest,
+    date_from: NaiveDate,
+    date_to: NaiveDate,
+    organization_ref: Option<&str>,
+) -> Result<SkuPnlResponse> {
+    let (previous_from, previous_to) = previous_period(date_from, date_to);
+    let connection_mp_ref = filters
+        .connection_mp_ref
+        .as_deref()
+        .filter(|value| !value.is_empty());
+    let days = repository::sku_days(
+        &format_date(previous_from),
+        &format_date(date_to),
+        connection_mp_ref,
+        organization_ref,
+    )
+    .await?;
+
+    let mut rows = fold_rows(days, date_from, date_to);
+    let ids: Vec<String> = rows
+        .iter()
+        .filter_map(|row| row.nomenclature_ref.clone())
+        .collect();
+    let names = repository::nomenclature_names(&ids).await?;
+
+    let mut totals = SkuPnlMetrics::default();
+    let mut previous_totals = SkuPnlMetrics::default();
+    for row in &mut rows {
+        totals.add(&row.current);
+        previous_totals.add(&row.previous);
+        if let Some((article, description)) =
+            row.nomenclature_ref.as_ref().and_then(|id| names.get(id))
+        {
+            if !article.is_empty() {
+                row.article = article.clone();
+            }
+            row.description = description.clone();
+        }
+    }
+
+    Ok(SkuPnlResponse {
+        filters,
+        previous_from: format_date(previous_from),
+        previous_to: format_date(previous_to),
+        totals,
+        previous_totals,
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(sku: &str, day: &str, payout: f64) -> SkuDay {
+        SkuDay {
+            sku_key: sku.to_string(),
+            nomenclature_ref: sku.to_string(),
+            article: format!("ART-{sku}"),
+            day: day.to_string(),
+            metrics: SkuPnlMetrics {
+                revenue: payout * 1.3,
+                payout,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn days_split_into_current_previous_and_trend() {
+        let from = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 3, 7).unwrap();
+        let rows = fold_rows(
+            vec![
+                day("a", "2026-02-27", 50.0),
+                day("a", "2026-03-01", 100.0),
+                day("a", "2026-03-07", 40.0),
+                day("b", "2026-03-03", 500.0),
+            ],
+            from,
+            to,
+        );
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].sku_key, "b");
+        let a = &rows[1];
+        assert_eq!(a.current.payout, 140.0);
+        assert_eq!(a.previous.payout, 50.0);
+        assert_eq!(a.trend.len(), 7);
+        assert_eq!(a.trend[0], 100.0);
+        assert_eq!(a.trend[6], 40.0);
+        assert_eq!(rows[0].previous, SkuPnlMetrics::default());
+    }
+}
@@ -1 +1,3 @@
 pub mod d400_monthly_summary;
+pub mod d414_import_coverage;
+pub mod d415_sku_pnl;
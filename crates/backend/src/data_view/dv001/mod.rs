@@ -18,6 +18,9 @@ use contracts::shared::drilldown::{DrilldownResponse, DrilldownRow, MetricColumn
 use sea_orm::{ConnectionTrait, FromQueryResult, Statement};
 
 use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+
+use super::{nomenclature_ref, scoped_source};
 
 // ---------------------------------------------------------------------------
 // Helpers
@@ -243,13 +246,18 @@ async fn fetch_aggregate(
     date_from: &str,
     date_to: &str,
     connection_mp_refs: &[String],
+    nomenclature_ref: Option<&str>,
 ) -> Result<f64> {
     let db = get_connection();
+    let source = scoped_source(
+        projection_archive::source("p904_sales_data", Some(date_from)),
+        nomenclature_ref,
+    );
 
     let mut sql = format!(
         r#"
         SELECT {metric_expr} AS total
-        FROM p904_sales_data p
+        FROM {source} p
         WHERE substr(p.date, 1, 10) >= ? AND substr(p.date, 1, 10) <= ?
         "#,
         metric_expr = metric.aggregate_sql("p")
@@ -287,8 +295,13 @@ async fn fetch_daily_rows(
     date_from: &str,
     date_to: &str,
     connection_mp_refs: &[String],
+    nomenclature_ref: Option<&str>,
 ) -> Result<Vec<DailyRow>> {
     let db = get_connection();
+    let source = scoped_source(
+        projection_archive::source("p904_sales_data", Some(date_from)),
+        nomenclature_ref,
+    );
 
     let mut sql = format!(
         r#"
@@ -296,7 +309,7 @@ async fn fetch_daily_rows(
             printf('%06d', CAST(julianday(DATE(t.date)) - julianday(?) AS INTEGER)) AS day_offset,
             DATE(t.date) AS day_label,
             {metric_expr} AS total
-        FROM p904_sales_data t
+        FROM {source} t
         WHERE substr(t.date, 1, 10) >= ? AND substr(t.date, 1, 10) <= ?
         "#,
         metric_expr = metric.aggregate_sql("t")
@@ -339,8 +352,13 @@ async fn fetch_drilldown_period(
     date_from: &str,
     date_to: &str,
     connection_mp_refs: &[String],
+    nomenclature_ref: Option<&str>,
 ) -> Result<Vec<DrilldownAggRow>> {
     let db = get_connection();
+    let source = scoped_source(
+        projection_archive::source("p904_sales_data", Some(date_from)),
+        nomenclature_ref,
+    );
 
     // Date grouping: use day-offset as group_key so P1 and P2 rows align correctly.
     if group_col == "date" {
@@ -350,7 +368,7 @@ async fn fetch_drilldown_period(
                 printf('%06d', CAST(julianday(DATE(t.date)) - julianday(?) AS INTEGER)) AS group_key,
                 DATE(t.date) AS label,
                 {metric_expr} AS total
-            FROM p904_sales_data t
+            FROM {source} t
             WHERE substr(t.date, 1, 10) >= ? AND substr(t.date, 1, 10) <= ?
             "#,
             metric_expr = metric.aggregate_sql("t")
@@ -388,7 +406,7 @@ async fn fetch_drilldown_period(
                 COALESCE({alias}.{group_col}, '') AS group_key,
                 COALESCE({alias}.{group_col}, '') AS label,
                 {metric_expr} AS total
-            FROM p904_sales_data t
+            FROM {source} t
             LEFT JOIN {src_tbl} {alias} ON t.{join_col} = {alias}.id
             WHERE substr(t.date, 1, 10) >= ? AND substr(t.date, 1, 10) <= ?
             "#,
@@ -434,7 +452,7 @@ async fn fetch_drilldown_period(
             COALESCE(t.{group_col}, '') AS group_key,
             COALESCE({select_label}, '') AS label,
             {metric_expr} AS total
-        FROM p904_sales_data t
+        FROM {source} t
         {join_clause}
         WHERE substr(t.date, 1, 10) >= ? AND substr(t.date, 1, 10) <= ?
         "#,
@@ -476,8 +494,13 @@ async fn fetch_drilldown_multi_period(
     date_from: &str,
     date_to: &str,
     connection_mp_refs: &[String],
+    nomenclature_ref: Option<&str>,
 ) -> Result<Vec<(String, String, Vec<f64>)>> {
     let db = get_connection();
+    let source = scoped_source(
+        projection_archive::source("p904_sales_data", Some(date_from)),
+        nomenclature_ref,
+    );
 
     // Build metric SELECT columns: SUM(expr) AS m0, SUM(expr) AS m1, ...
     let metric_cols: String = metrics
@@ -497,7 +520,7 @@ async fn fetch_drilldown_multi_period(
                 printf('%06d', CAST(julianday(DATE(t.date)) - julianday(?) AS INTEGER)) AS group_key,
                 DATE(t.date) AS label,
                 {metric_cols}
-            FROM p904_sales_data t
+            FROM {source} t
             WHERE substr(t.date, 1, 10) >= ? AND substr(t.date, 1, 10) <= ?
             "#
         );
@@ -514,7 +537,7 @@ async fn fetch_drilldown_multi_period(
                 COALESCE({alias}.{group_col}, '') AS group_key,
                 COALESCE({alias}.{group_col}, '') AS label,
                 {metric_cols}
-            FROM p904_sales_data t
+            FROM {source} t
             LEFT JOIN {src_tbl} {alias} ON t.{join_col} = {alias}.id
             WHERE substr(t.date, 1, 10) >= ? AND substr(t.date, 1, 10) <= ?
             "#
@@ -537,7 +560,7 @@ async fn fetch_drilldown_multi_period(
                 COALESCE(t.{group_col}, '') AS group_key,
                 COALESCE({select_label}, '') AS label,
                 {metric_cols}
-            FROM p904_sales_data t
+            FROM {source} t
             {join_clause}
             WHERE substr(t.date, 1, 10) >= ? AND substr(t.date, 1, 10) <= ?
             "#,
@@ -615,6 +638,7 @@ async fn fetch_drilldown_multi_period(
 /// P2 — агрегат одним запросом.
 /// Все запросы выполняются параллельно через `tokio::join!`.
 pub async fn compute_scalar(ctx: &ViewContext) -> Result<IndicatorValue> {
+    let nomenclature = nomenclature_ref(ctx)?;
     let metric = resolve_metric(ctx);
     let (p2_from, p2_to) = resolve_period2(ctx);
 
@@ -623,15 +647,23 @@ pub async fn compute_scalar(ctx: &ViewContext) -> Result<IndicatorValue> {
             metric,
             &ctx.date_from,
             &ctx.date_to,
-            &ctx.connection_mp_refs
+            &ctx.connection_mp_refs,
+            nomenclature.as_deref()
         ),
         fetch_daily_rows(
             metric,
             &ctx.date_from,
             &ctx.date_to,
-            &ctx.connection_mp_refs
+            &ctx.connection_mp_refs,
+            nomenclature.as_deref()
+        ),
+        fetch_aggregate(
+            metric,
+            &p2_from,
+            &p2_to,
+            &ctx.connection_mp_refs,
+            nomenclature.as_deref()
         ),
-        fetch_aggregate(metric, &p2_from, &p2_to, &ctx.connection_mp_refs),
     );
 
     let cur = current_result?;
@@ -663,6 +695,7 @@ pub async fn compute_scalar(ctx: &ViewContext) -> Result<IndicatorValue> {
 /// Данные для обоих периодов запрашиваются всегда по дням при group_by == "date",
 /// иначе — агрегаты по выбранному измерению.
 pub async fn compute_drilldown(ctx: &ViewContext, group_by: &str) -> Result<DrilldownResponse> {
+    let nomenclature = nomenclature_ref(ctx)?;
     let dim = meta()
         .available_dimensions
         .into_iter()
@@ -698,6 +731,7 @@ pub async fn compute_drilldown(ctx: &ViewContext, group_by: &str) -> Result<Dril
             &ctx.date_from,
             &ctx.date_to,
             &ctx.connection_mp_refs,
+            nomenclature.as_deref(),
         ),
         fetch_drilldown_period(
             &db_col,
@@ -709,6 +743,7 @@ pub async fn compute_drilldown(ctx: &ViewContext, group_by: &str) -> Result<Dril
             &p2_from,
             &p2_to,
             &ctx.connection_mp_refs,
+            nomenclature.as_deref(),
         ),
     );
 
@@ -823,6 +858,7 @@ pub async fn compute_drilldown_multi(
     group_by: &str,
     metric_ids: &[String],
 ) -> Result<DrilldownResponse> {
+    let nomenclature = nomenclature_ref(ctx)?;
     if metric_ids.is_empty() {
         return compute_drilldown(ctx, group_by).await;
     }
@@ -863,6 +899,7 @@ pub async fn compute_drilldown_multi(
             &ctx.date_from,
             &ctx.date_to,
             &ctx.connection_mp_refs,
+            nomenclature.as_deref(),
         ),
         fetch_drilldown_multi_period(
             &db_col,
@@ -874,6 +911,7 @@ pub async fn compute_drilldown_multi(
             &p2_from,
             &p2_to,
             &ctx.connection_mp_refs,
+            nomenclature.as_deref(),
         ),
     );
 
@@ -12,6 +12,8 @@ use sea_orm::{ConnectionTrait, FromQueryResult, Statement};
 
 use crate::shared::data::db::get_connection;
 
+use super::{nomenclature_ref, scoped_source};
+
 fn fmt_day_label(iso: &str) -> String {
     let date_part = iso.split('T').next().unwrap_or(iso);
     let parts: Vec<&str> = date_part.split('-').collect();
@@ -156,13 +158,15 @@ async fn fetch_aggregate(
     date_from: &str,
     date_to: &str,
     connection_mp_refs: &[String],
+    nomenclature_ref: Option<&str>,
 ) -> Result<f64> {
     let db = get_connection();
+    let source = scoped_source("p911_wb_advert_by_items".to_string(), nomenclature_ref);
 
     let mut sql = format!(
         r#"
         SELECT {metric_expr} AS total
-        FROM p911_wb_advert_by_items p
+        FROM {source} p
         WHERE substr(p.entry_date, 1, 10) >= ? AND substr(p.entry_date, 1, 10) <= ?
         "#,
         metric_expr = metric.aggregate_sql("p")
@@ -196,8 +200,10 @@ async fn fetch_daily_rows(
     date_from: &str,
     date_to: &str,
     connection_mp_refs: &[String],
+    nomenclature_ref: Option<&str>,
 ) -> Result<Vec<DailyRow>> {
     let db = get_connection();
+    let source = scoped_source("p911_wb_advert_by_items".to_string(), nomenclature_ref);
 
     let mut sql = format!(
         r#"
@@ -205,7 +211,7 @@ async fn fetch_daily_rows(
             printf('%06d', CAST(julianday(DATE(t.entry_date)) - julianday(?) AS INTEGER)) AS day_offset,
             DATE(t.entry_date) AS day_label,
             {metric_expr} AS total
-        FROM p911_wb_advert_by_items t
+        FROM {source} t
         WHERE substr(t.entry_date, 1, 10) >= ? AND substr(t.entry_date, 1, 10) <= ?
         "#,
         metric_expr = metric.aggregate_sql("t")
@@ -244,8 +250,10 @@ async fn fetch_drilldown_period(
     date_from: &str,
     date_to: &str,
     connection_mp_refs: &[String],
+    nomenclature_ref: Option<&str>,
 ) -> Result<Vec<DrilldownAggRow>> {
     let db = get_connection();
+    let source = scoped_source("p911_wb_advert_by_items".to_string(), nomenclature_ref);
 
     if group_col == "entry_date" {
         let mut sql = format!(
@@ -254,7 +262,7 @@ async fn fetch_drilldown_period(
                 printf('%06d', CAST(julianday(DATE(t.entry_date)) - julianday(?) AS INTEGER)) AS group_key,
                 DATE(t.entry_date) AS label,
                 {metric_expr} AS total
-            FROM p911_wb_advert_by_items t
+            FROM {source} t
             WHERE substr(t.entry_date, 1, 10) >= ? AND substr(t.entry_date, 1, 10) <= ?
             "#,
             metric_expr = metric.aggregate_sql("t")
@@ -291,7 +299,7 @@ async fn fetch_drilldown_period(
                 COALESCE({alias}.{group_col}, '') AS group_key,
                 COALESCE({alias}.{group_col}, '') AS label,
                 {metric_expr} AS total
-            FROM p911_wb_advert_by_items t
+            FROM {source} t
             LEFT JOIN {src_tbl} {alias} ON t.{join_col} = {alias}.id
             WHERE substr(t.entry_date, 1, 10) >= ? AND substr(t.entry_date, 1, 10) <= ?
             "#,
@@ -336,7 +344,7 @@ async fn fetch_drilldown_period(
             COALESCE(t.{group_col}, '') AS group_key,
             COALESCE({select_label}, '') AS label,
             {metric_expr} AS total
-        FROM p911_wb_advert_by_items t
+        FROM {source} t
         {join_clause}
         WHERE substr(t.entry_date, 1, 10) >= ? AND substr(t.entry_date, 1, 10) <= ?
         "#,
@@ -373,8 +381,10 @@ async fn fetch_drilldown_multi_period(
     date_from: &str,
     date_to: &str,
     connection_mp_refs: &[String],
+    nomenclature_ref: Option<&str>,
 ) -> Result<Vec<(String, String, Vec<f64>)>> {
     let db = get_connection();
+    let source = scoped_source("p911_wb_advert_by_items".to_string(), nomenclature_ref);
 
     let metric_cols: String = metrics
         .iter()
@@ -393,7 +403,7 @@ async fn fetch_drilldown_multi_period(
                 printf('%06d', CAST(julianday(DATE(t.entry_date)) - julianday(?) AS INTEGER)) AS group_key,
                 DATE(t.entry_date) AS label,
                 {metric_cols}
-            FROM p911_wb_advert_by_items t
+            FROM {source} t
             WHERE substr(t.entry_date, 1, 10) >= ? AND substr(t.entry_date, 1, 10) <= ?
             "#
         );
@@ -410,7 +420,7 @@ async fn fetch_drilldown_multi_period(
                 COALESCE({alias}.{group_col}, '') AS group_key,
                 COALESCE({alias}.{group_col}, '') AS label,
                 {metric_cols}
-            FROM p911_wb_advert_by_items t
+            FROM {source} t
             LEFT JOIN {src_tbl} {alias} ON t.{join_col} = {alias}.id
             WHERE substr(t.entry_date, 1, 10) >= ? AND substr(t.entry_date, 1, 10) <= ?
             "#
@@ -433,7 +443,7 @@ async fn fetch_drilldown_multi_period(
                 COALESCE(t.{group_col}, '') AS group_key,
                 COALESCE({select_label}, '') AS label,
                 {metric_cols}
-            FROM p911_wb_advert_by_items t
+            FROM {source} t
             {join_clause}
             WHERE substr(t.entry_date, 1, 10) >= ? AND substr(t.entry_date, 1, 10) <= ?
             "#,
@@ -500,6 +510,7 @@ async fn fetch_drilldown_multi_period(
 }
 
 pub async fn compute_scalar(ctx: &ViewContext) -> Result<IndicatorValue> {
+    let nomenclature = nomenclature_ref(ctx)?;
     let metric = resolve_metric(ctx);
     let (p2_from, p2_to) = resolve_period2(ctx);
 
@@ -508,15 +519,23 @@ pub async fn compute_scalar(ctx: &ViewContext) -> Result<IndicatorValue> {
             metric,
             &ctx.date_from,
             &ctx.date_to,
-            &ctx.connection_mp_refs
+            &ctx.connection_mp_refs,
+            nomenclature.as_deref()
         ),
         fetch_daily_rows(
             metric,
             &ctx.date_from,
             &ctx.date_to,
-            &ctx.connection_mp_refs
+            &ctx.connection_mp_refs,
+            nomenclature.as_deref()
+        ),
+        fetch_aggregate(
+            metric,
+            &p2_from,
+            &p2_to,
+            &ctx.connection_mp_refs,
+            nomenclature.as_deref()
         ),
-        fetch_aggregate(metric, &p2_from, &p2_to, &ctx.connection_mp_refs),
     );
 
     let cur = current_result?;
@@ -539,6 +558,7 @@ pub async fn compute_scalar(ctx: &ViewContext) -> Result<IndicatorValue> {
 }
 
 pub async fn compute_drilldown(ctx: &ViewContext, group_by: &str) -> Result<DrilldownResponse> {
+    let nomenclature = nomenclature_ref(ctx)?;
     let dim = meta()
         .available_dimensions
         .into_iter()
@@ -564,6 +584,7 @@ pub async fn compute_drilldown(ctx: &ViewContext, group_by: &str) -> Result<Dril
             &ctx.date_from,
             &ctx.date_to,
             &ctx.connection_mp_refs,
+            nomenclature.as_deref(),
         ),
         fetch_drilldown_period(
             &db_col,
@@ -575,6 +596,7 @@ pub async fn compute_drilldown(ctx: &ViewContext, group_by: &str) -> Result<Dril
             &p2_from,
             &p2_to,
             &ctx.connection_mp_refs,
+            nomenclature.as_deref(),
         ),
     );
 
@@ -664,6 +686,7 @@ pub async fn compute_drilldown_multi(
     group_by: &str,
     metric_ids: &[String],
 ) -> Result<DrilldownResponse> {
+    let nomenclature = nomenclature_ref(ctx)?;
     if metric_ids.is_empty() {
         return compute_drilldown(ctx, group_by).await;
     }
@@ -703,6 +726,7 @@ pub async fn compute_drilldown_multi(
             &ctx.date_from,
             &ctx.date_to,
             &ctx.connection_mp_refs,
+            nomenclature.as_deref(),
         ),
         fetch_drilldown_multi_period(
             &db_col,
@@ -714,6 +738,7 @@ pub async fn compute_drilldown_multi(
             &p2_from,
             &p2_to,
             &ctx.connection_mp_refs,
+            nomenclature.as_deref(),
         ),
     );
 
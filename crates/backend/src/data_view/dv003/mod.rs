@@ -11,6 +11,8 @@ use sea_orm::{ConnectionTrait, FromQueryResult, Statement};
 
 use crate::shared::data::db::get_connection;
 
+use super::{nomenclature_ref, scoped_source};
+
 fn fmt_day_label(iso: &str) -> String {
     let date_part = iso.split('T').next().unwrap_or(iso);
     let parts: Vec<&str> = date_part.split('-').collect();
@@ -243,13 +245,15 @@ async fn fetch_aggregate(
     date_from: &str,
     date_to: &str,
     connection_mp_refs: &[String],
+    nomenclature_ref: Option<&str>,
 ) -> Result<f64> {
     let db = get_connection();
+    let source = scoped_source("p909_mp_order_line_turnovers".to_string(), nomenclature_ref);
 
     let mut sql = format!(
         r#"
         SELECT CAST(COALESCE(SUM(COALESCE(p.amount, 0)), 0) AS REAL) AS total
-        FROM p909_mp_order_line_turnovers p
+        FROM {source} p
         WHERE p.layer = 'oper'
           AND p.entry_date >= ? AND p.entry_date <= ?
           AND ({metric_predicate})
@@ -285,8 +289,10 @@ async fn fetch_daily_rows(
     date_from: &str,
     date_to: &str,
     connection_mp_refs: &[String],
+    nomenclature_ref: Option<&str>,
 ) -> Result<Vec<DailyRow>> {
     let db = get_connection();
+    let source = scoped_source("p909_mp_order_line_turnovers".to_string(), nomenclature_ref);
 
     let mut sql = format!(
         r#"
@@ -294,7 +300,7 @@ async fn fetch_daily_rows(
             printf('%06d', CAST(julianday(DATE(t.entry_date)) - julianday(?) AS INTEGER)) AS day_offset,
             DATE(t.entry_date) AS day_label,
             CAST(COALESCE(SUM(COALESCE(t.amount, 0)), 0) AS REAL) AS total
-        FROM p909_mp_order_line_turnovers t
+        FROM {source} t
         WHERE t.layer = 'oper'
           AND t.entry_date >= ? AND t.entry_date <= ?
           AND ({metric_predicate})
@@ -335,8 +341,10 @@ async fn fetch_drilldown_period(
     date_from: &str,
     date_to: &str,
     connection_mp_refs: &[String],
+    nomenclature_ref: Option<&str>,
 ) -> Result<Vec<DrilldownAggRow>> {
     let db = get_connection();
+    let source = scoped_source("p909_mp_order_line_turnovers".to_string(), nomenclature_ref);
 
     if group_col == "entry_date" {
         let mut sql = format!(
@@ -345,7 +353,7 @@ async fn fetch_drilldown_period(
                 printf('%06d', CAST(julianday(DATE(t.entry_date)) - julianday(?) AS INTEGER)) AS group_key,
                 DATE(t.entry_date) AS label,
                 CAST(COALESCE(SUM(COALESCE(t.amount, 0)), 0) AS REAL) AS total
-            FROM p909_mp_order_line_turnovers t
+            FROM {source} t
             WHERE t.layer = 'oper'
               AND t.entry_date >= ? AND t.entry_date <= ?
               AND ({metric_predicate})
@@ -383,7 +391,7 @@ async fn fetch_drilldown_period(
                 COALESCE({alias}.{group_col}, '') AS group_key,
                 COALESCE({alias}.{group_col}, '') AS label,
                 CAST(COALESCE(SUM(COALESCE(t.amount, 0)), 0) AS REAL) AS total
-            FROM p909_mp_order_line_turnovers t
+            FROM {source} t
             LEFT JOIN {src_tbl} {alias} ON t.{join_col} = {alias}.id
             WHERE t.layer = 'oper'
               AND t.entry_date >= ? AND t.entry_date <= ?
@@ -429,7 +437,7 @@ async fn fetch_drilldown_period(
             COALESCE(t.{group_col}, '') AS group_key,
             COALESCE({select_label}, '') AS label,
             CAST(COALESCE(SUM(COALESCE(t.amount, 0)), 0) AS REAL) AS total
-        FROM p909_mp_order_line_turnovers t
+        FROM {source} t
         {join_clause}
         WHERE t.layer = 'oper'
           AND t.entry_date >= ? AND t.entry_date <= ?
@@ -467,8 +475,10 @@ async fn fetch_drilldown_multi_period(
     date_from: &str,
     date_to: &str,
     connection_mp_refs: &[String],
+    nomenclature_ref: Option<&str>,
 ) -> Result<Vec<(String, String, Vec<f64>)>> {
     let db = get_connection();
+    let source = scoped_source("p909_mp_order_line_turnovers".to_string(), nomenclature_ref);
 
     let metric_cols: String = metrics
         .iter()
@@ -487,7 +497,7 @@ async fn fetch_drilldown_multi_period(
                 printf('%06d', CAST(julianday(DATE(t.entry_date)) - julianday(?) AS INTEGER)) AS group_key,
                 DATE(t.entry_date) AS label,
                 {metric_cols}
-            FROM p909_mp_order_line_turnovers t
+            FROM {source} t
             WHERE t.layer = 'oper'
               AND t.entry_date >= ? AND t.entry_date <= ?
             "#
@@ -505,7 +515,7 @@ async fn fetch_drilldown_multi_period(
                 COALESCE({alias}.{group_col}, '') AS group_key,
                 COALESCE({alias}.{group_col}, '') AS label,
                 {metric_cols}
-            FROM p909_mp_order_line_turnovers t
+            FROM {source} t
             LEFT JOIN {src_tbl} {alias} ON t.{join_col} = {alias}.id
             WHERE t.layer = 'oper'
               AND t.entry_date >= ? AND t.entry_date <= ?
@@ -529,7 +539,7 @@ async fn fetch_drilldown_multi_period(
                 COALESCE(t.{group_col}, '') AS group_key,
                 COALESCE({select_label}, '') AS label,
                 {metric_cols}
-            FROM p909_mp_order_line_turnovers t
+            FROM {source} t
             {join_clause}
             WHERE t.layer = 'oper'
               AND t.entry_date >= ? AND t.entry_date <= ?
@@ -597,6 +607,7 @@ async fn fetch_drilldown_multi_period(
 }
 
 pub async fn compute_scalar(ctx: &ViewContext) -> Result<IndicatorValue> {
+    let nomenclature = nomenclature_ref(ctx)?;
     let metric = resolve_metric(ctx);
     let (p2_from, p2_to) = resolve_period2(ctx);
 
@@ -605,15 +616,23 @@ pub async fn compute_scalar(ctx: &ViewContext) -> Result<IndicatorValue> {
             metric,
             &ctx.date_from,
             &ctx.date_to,
-            &ctx.connection_mp_refs
+            &ctx.connection_mp_refs,
+            nomenclature.as_deref()
         ),
         fetch_daily_rows(
             metric,
             &ctx.date_from,
             &ctx.date_to,
-            &ctx.connection_mp_refs
+            &ctx.connection_mp_refs,
+            nomenclature.as_deref()
+        ),
+        fetch_aggregate(
+            metric,
+            &p2_from,
+            &p2_to,
+            &ctx.connection_mp_refs,
+            nomenclature.as_deref()
         ),
-        fetch_aggregate(metric, &p2_from, &p2_to, &ctx.connection_mp_refs),
     );
 
     let current = current_result?;
@@ -635,6 +654,7 @@ pub async fn compute_scalar(ctx: &ViewContext) -> Result<IndicatorValue> {
 }
 
 pub async fn compute_drilldown(ctx: &ViewContext, group_by: &str) -> Result<DrilldownResponse> {
+    let nomenclature = nomenclature_ref(ctx)?;
     let dim = meta()
         .available_dimensions
         .into_iter()
@@ -660,6 +680,7 @@ pub async fn compute_drilldown(ctx: &ViewContext, group_by: &str) -> Result<Dril
             &ctx.date_from,
             &ctx.date_to,
             &ctx.connection_mp_refs,
+            nomenclature.as_deref(),
         ),
         fetch_drilldown_period(
             &db_col,
@@ -671,6 +692,7 @@ pub async fn compute_drilldown(ctx: &ViewContext, group_by: &str) -> Result<Dril
             &p2_from,
             &p2_to,
             &ctx.connection_mp_refs,
+            nomenclature.as_deref(),
         ),
     );
 
@@ -761,6 +783,7 @@ pub async fn compute_drilldown_multi(
     group_by: &str,
     metric_ids: &[String],
 ) -> Result<DrilldownResponse> {
+    let nomenclature = nomenclature_ref(ctx)?;
     if metric_ids.is_empty() {
         return compute_drilldown(ctx, group_by).await;
     }
@@ -798,6 +821,7 @@ pub async fn compute_drilldown_multi(
             &ctx.date_from,
             &ctx.date_to,
             &ctx.connection_mp_refs,
+            nomenclature.as_deref(),
         ),
         fetch_drilldown_multi_period(
             &db_col,
@@ -809,6 +833,7 @@ pub async fn compute_drilldown_multi(
             &p2_from,
             &p2_to,
             &ctx.connection_mp_refs,
+            nomenclature.as_deref(),
         ),
     );
 
@@ -155,6 +155,52 @@ async fn wait_for_in_flight(entry: Arc<InFlightEntry>) -> Result<IndicatorValue>
     }
 }
 
+// ---------------------------------------------------------------------------
+// Nomenclature slice (cross-filter)
+// ---------------------------------------------------------------------------
+
+/// Ключ `ViewContext::params` со срезом по номенклатуре (кросс-фильтр дашборда).
+pub const NOMENCLATURE_PARAM: &str = "nomenclature_ref";
+
+/// Срез по номенклатуре из контекста. Значение обязано быть UUID — после проверки
+/// его можно подставлять в SQL литералом.
+pub fn nomenclature_ref(ctx: &ViewContext) -> Result<Option<String>> {
+    let Some(value) = ctx.params.get(NOMENCLATURE_PARAM) else {
+        return Ok(None);
+    };
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    uuid::Uuid::parse_str(value)
+        .map_err(|_| anyhow::anyhow!("Некорректный {NOMENCLATURE_PARAM}: {value}"))?;
+    Ok(Some(value.to_string()))
+}
+
+/// Источник фактов, суженный до одной номенклатуры, если срез задан.
+/// Таблица-источник должна содержать колонку `nomenclature_ref`.
+pub fn scoped_source(source: String, nomenclature_ref: Option<&str>) -> String {
+    match nomenclature_ref {
+        Some(nomenclature_ref) => {
+            format!("(SELECT * FROM {source} WHERE nomenclature_ref = '{nomenclature_ref}')")
+        }
+        None => source,
+    }
+}
+
+/// Срез по номенклатуре понимают только view с измерением `nomenclature_ref`;
+/// остальные вернули бы несуженные цифры, поэтому такой запрос — ошибка.
+fn ensure_nomenclature_supported(meta: &DataViewMeta, ctx: &ViewContext) -> Result<()> {
+    let supported = meta
+        .available_dimensions
+        .iter()
+        .any(|dimension| dimension.id == NOMENCLATURE_PARAM);
+    if !supported && nomenclature_ref(ctx)?.is_some() {
+        anyhow::bail!("DataView {} не поддерживает срез по номенклатуре", meta.id);
+    }
+    Ok(())
+}
+
 type ScalarFn =
     fn(&ViewContext) -> Pin<Box<dyn Future<Output = Result<IndicatorValue>> + Send + '_>>;
 
@@ -286,6 +332,9 @@ impl DataViewRegistry {
     /// Повторные вызовы с теми же параметрами (например, из нескольких индикаторов
     /// одного дашборда) не делают дополнительных запросов к БД.
     pub async fn compute_scalar(&self, view_id: &str, ctx: &ViewContext) -> Result<IndicatorValue> {
+        if let Some(meta) = self.get_meta(view_id) {
+            ensure_nomenclature_supported(meta, ctx)?;
+        }
         let key = cache_key(view_id, ctx);
 
         if let Some(cached) = cache_get(&key) {
@@ -333,6 +382,7 @@ impl DataViewRegistry {
             .views
             .get(view_id)
             .ok_or_else(|| anyhow::anyhow!("DataView not found: {}", view_id))?;
+        ensure_nomenclature_supported(&entry.meta, ctx)?;
         (entry.drilldown)(ctx, group_by, metric_ids).await
     }
 
@@ -396,3 +446,50 @@ impl Default for DataViewRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        dv001, dv005, ensure_nomenclature_supported, nomenclature_ref, scoped_source,
+        NOMENCLATURE_PARAM,
+    };
+    use contracts::shared::data_view::ViewContext;
+
+    fn ctx_with_nomenclature(value: &str) -> ViewContext {
+        let mut ctx = ViewContext::default();
+        ctx.params
+            .insert(NOMENCLATURE_PARAM.to_string(), value.to_string());
+        ctx
+    }
+
+    #[test]
+    fn nomenclature_slice_accepts_only_uuid() {
+        let id = "5d1f3c8e-2b7a-4c1e-9f0a-3e6b8d2c1a47";
+        assert_eq!(
+            nomenclature_ref(&ctx_with_nomenclature(id)).unwrap(),
+            Some(id.to_string())
+        );
+        assert_eq!(nomenclature_ref(&ViewContext::default()).unwrap(), None);
+        assert!(nomenclature_ref(&ctx_with_nomenclature("x' OR 1=1 --")).is_err());
+    }
+
+    #[test]
+    fn scoped_source_wraps_only_when_slice_is_set() {
+        assert_eq!(
+            scoped_source("p904_sales_data".into(), None),
+            "p904_sales_data"
+        );
+        assert_eq!(
+            scoped_source("p904_sales_data".into(), Some("abc")),
+            "(SELECT * FROM p904_sales_data WHERE nomenclature_ref = 'abc')"
+        );
+    }
+
+    #[test]
+    fn views_without_nomenclature_dimension_reject_the_slice() {
+        let ctx = ctx_with_nomenclature("5d1f3c8e-2b7a-4c1e-9f0a-3e6b8d2c1a47");
+        assert!(ensure_nomenclature_supported(&dv001::meta(), &ctx).is_ok());
+        assert!(ensure_nomenclature_supported(&dv005::meta(), &ctx).is_err());
+        assert!(ensure_nomenclature_supported(&dv005::meta(), &ViewContext::default()).is_ok());
+    }
+}
@@ -11,6 +11,7 @@ use sea_orm::entity::prelude::*;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set};
 
 use crate::shared::data::db::get_connection;
+use crate::shared::secrets;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "a006_connection_mp")]
@@ -74,13 +75,13 @@ impl From<Model> for ConnectionMP {
             ),
             marketplace_id: m.marketplace,
             organization_ref: m.organization_ref,
-            api_key: m.api_key,
+            api_key: open_secret(&m.api_key),
             supplier_id: m.supplier_id,
             application_id: m.application_id,
             is_used: m.is_used,
             business_account_id: m.business_account_id,
-            api_key_stats: m.api_key_stats,
-            test_mode: m.test_mode,
+            api_key_stats: m.api_key_stats.as_deref().map(open_secret),
+            is_sandbox: m.test_mode,
             planned_commission_percent: m.planned_commission_percent,
             planned_acquiring_percent: m.planned_acquiring_percent,
             authorization_type,
@@ -88,6 +89,19 @@ impl From<Model> for ConnectionMP {
     }
 }
 
+/// Расшифровка токена из БД; при ошибке (сменили ключ) — пустая строка,
+/// чтобы подключение читалось, а токен можно было ввести заново.
+fn open_secret(stored: &str) -> String {
+    secrets::decrypt(stored).unwrap_or_else(|e| {
+        tracing::warn!("a006: cannot decrypt API key: {}", e);
+        String::new()
+    })
+}
+
+fn seal_secret(plain: &str) -> anyhow::Result<String> {
+    secrets::encrypt(plain)
+}
+
 fn conn() -> &'static DatabaseConnection {
     get_connection()
 }
@@ -124,13 +138,17 @@ pub async fn insert(aggregate: &ConnectionMP) -> anyhow::Result<Uuid> {
         marketplace: Set(aggregate.marketplace_id.clone()),
         organization: Set(String::new()),
         organization_ref: Set(aggregate.organization_ref.clone()),
-        api_key: Set(aggregate.api_key.clone()),
+        api_key: Set(seal_secret(&aggregate.api_key)?),
         supplier_id: Set(aggregate.supplier_id.clone()),
         application_id: Set(aggregate.application_id.clone()),
         is_used: Set(aggregate.is_used),
         business_account_id: Set(aggregate.business_account_id.clone()),
-        api_key_stats: Set(aggregate.api_key_stats.clone()),
-        test_mode: Set(aggregate.test_mode),
+        api_key_stats: Set(aggregate
+            .api_key_stats
+            .as_deref()
+            .map(seal_secret)
+            .transpose()?),
+        test_mode: Set(aggregate.is_sandbox),
         planned_commission_percent: Set(aggregate.planned_commission_percent),
         planned_acquiring_percent: Set(aggregate.planned_acquiring_percent),
         authorization_type: Set(aggregate.authorization_type.as_str().to_string()),
@@ -154,13 +172,17 @@ pub async fn update(aggregate: &ConnectionMP) -> anyhow::Result<()> {
         marketplace: Set(aggregate.marketplace_id.clone()),
         organization: Set(String::new()),
         organization_ref: Set(aggregate.organization_ref.clone()),
-        api_key: Set(aggregate.api_key.clone()),
+        api_key: Set(seal_secret(&aggregate.api_key)?),
         supplier_id: Set(aggregate.supplier_id.clone()),
         application_id: Set(aggregate.application_id.clone()),
         is_used: Set(aggregate.is_used),
         business_account_id: Set(aggregate.business_account_id.clone()),
-        api_key_stats: Set(aggregate.api_key_stats.clone()),
-        test_mode: Set(aggregate.test_mode),
+        api_key_stats: Set(aggregate
+            .api_key_stats
+            .as_deref()
+            .map(seal_secret)
+            .transpose()?),
+        test_mode: Set(aggregate.is_sandbox),
         planned_commission_percent: Set(aggregate.planned_commission_percent),
         planned_acquiring_percent: Set(aggregate.planned_acquiring_percent),
         authorization_type: Set(aggregate.authorization_type.as_str().to_string()),
@@ -184,3 +206,28 @@ pub async fn soft_delete(id: Uuid) -> anyhow::Result<bool> {
         .await?;
     Ok(result.rows_affected > 0)
 }
+
+/// Зашифровать токены, сохранённые открытым текстом (до включения шифрования).
+/// Возвращает число обновлённых записей.
+pub async fn encrypt_plaintext_keys() -> anyhow::Result<usize> {
+    let rows = Entity::find().all(conn()).await?;
+    let mut updated = 0;
+    for m in rows {
+        let stats_plain = m
+            .api_key_stats
+            .as_deref()
+            .is_some_and(|v| !v.is_empty() && !secrets::is_encrypted(v));
+        let key_plain = !m.api_key.is_empty() && !secrets::is_encrypted(&m.api_key);
+        if !key_plain && !stats_plain {
+            continue;
+        }
+        let api_key = seal_secret(&m.api_key)?;
+        let api_key_stats = m.api_key_stats.as_deref().map(seal_secret).transpose()?;
+        let mut active: ActiveModel = m.into();
+        active.api_key = Set(api_key);
+        active.api_key_stats = Set(api_key_stats);
+        active.update(conn()).await?;
+        updated += 1;
+    }
+    Ok(updated)
+}
@@ -1,4 +1,6 @@
 use super::repository;
+use crate::shared::marketplaces::sandbox;
+use crate::shared::secrets;
 use chrono::Utc;
 use contracts::domain::a006_connection_mp::aggregate::{
     ConnectionMP, ConnectionMPDto, ConnectionTestResult,
@@ -26,7 +28,7 @@ pub async fn create(dto: ConnectionMPDto) -> anyhow::Result<Uuid> {
     aggregate.is_used = dto.is_used;
     aggregate.business_account_id = dto.business_account_id;
     aggregate.api_key_stats = dto.api_key_stats;
-    aggregate.test_mode = dto.test_mode;
+    aggregate.is_sandbox = dto.is_sandbox;
     aggregate.authorization_type = dto.authorization_type;
 
     // Валидация
@@ -42,7 +44,7 @@ pub async fn create(dto: ConnectionMPDto) -> anyhow::Result<Uuid> {
 }
 
 /// Обновление существующего подключения
-pub async fn update(dto: ConnectionMPDto) -> anyhow::Result<()> {
+pub async fn update(mut dto: ConnectionMPDto) -> anyhow::Result<()> {
     let id = dto
         .id
         .as_ref()
@@ -53,6 +55,7 @@ pub async fn update(dto: ConnectionMPDto) -> anyhow::Result<()> {
         .await?
         .ok_or_else(|| anyhow::anyhow!("Not found"))?;
 
+    restore_masked_keys(&mut dto, &aggregate);
     aggregate.update(&dto);
 
     // Валидация
@@ -82,9 +85,51 @@ pub async fn list_all() -> anyhow::Result<Vec<ConnectionMP>> {
     repository::list_all().await
 }
 
+/// Копия подключения для ответа API: токены заменены маской.
+pub fn masked(mut item: ConnectionMP) -> ConnectionMP {
+    item.api_key = secrets::mask(&item.api_key);
+    item.api_key_stats = item.api_key_stats.as_deref().map(secrets::mask);
+    item
+}
+
+/// Клиент получает токены в маскированном виде и возвращает маску, если
+/// пользователь их не менял, — подставляем сохранённые значения.
+fn restore_masked_keys(dto: &mut ConnectionMPDto, stored: &ConnectionMP) {
+    if secrets::is_masked(&dto.api_key) {
+        dto.api_key = stored.api_key.clone();
+    }
+    if dto.api_key_stats.as_deref().is_some_and(secrets::is_masked) {
+        dto.api_key_stats = stored.api_key_stats.clone();
+    }
+}
+
+/// Для проверки уже сохранённого подключения с маскированными токенами.
+async fn with_stored_keys(mut dto: ConnectionMPDto) -> anyhow::Result<ConnectionMPDto> {
+    let masked = secrets::is_masked(&dto.api_key)
+        || dto.api_key_stats.as_deref().is_some_and(secrets::is_masked);
+    if !masked {
+        return Ok(dto);
+    }
+    let stored = match dto.id.as_deref().and_then(|s| Uuid::parse_str(s).ok()) {
+        Some(id) => repository::get_by_id(id).await?,
+        None => None,
+    };
+    match stored {
+        Some(stored) => restore_masked_keys(&mut dto, &stored),
+        None => dto.api_key.clear(),
+    }
+    Ok(dto)
+}
+
+/// Зашифровать токены, сохранённые до включения `[secrets].encryption_key`.
+pub async fn encrypt_stored_keys() -> anyhow::Result<usize> {
+    repository::encrypt_plaintext_keys().await
+}
+
 /// Получение информации о кабинете маркетплейса
 pub async fn seller_info(dto: ConnectionMPDto) -> anyhow::Result<ConnectionTestResult> {
     let start = std::time::Instant::now();
+    let dto = with_stored_keys(dto).await?;
 
     if dto.api_key.trim().is_empty() {
         return Ok(ConnectionTestResult {
@@ -179,10 +224,24 @@ pub async fn seller_info(dto: ConnectionMPDto) -> anyhow::Result<ConnectionTestR
         }
     };
 
-    let url = "https://common-api.wildberries.ru/api/v1/seller-info";
+    let url = match sandbox::resolve(
+        dto.is_sandbox,
+        "https://common-api.wildberries.ru/api/v1/seller-info",
+    ) {
+        Ok(url) => url,
+        Err(e) => {
+            return Ok(ConnectionTestResult {
+                success: false,
+                message: "Песочница недоступна".into(),
+                duration_ms: 0,
+                tested_at: Utc::now(),
+                details: Some(format!("{}", e)),
+            });
+        }
+    };
 
     let response = match client
-        .get(url)
+        .get(&url)
         .header("Authorization", api_key.as_str())
         .send()
         .await
@@ -242,6 +301,7 @@ pub async fn seller_info(dto: ConnectionMPDto) -> anyhow::Result<ConnectionTestR
 /// Тестирование подключения к маркетплейсу
 pub async fn test_connection(dto: ConnectionMPDto) -> anyhow::Result<ConnectionTestResult> {
     let start = std::time::Instant::now();
+    let dto = with_stored_keys(dto).await?;
 
     // Валидация базовых данных
     if dto.api_key.trim().is_empty() {
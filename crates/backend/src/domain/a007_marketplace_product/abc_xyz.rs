@@ -0,0 +1,446 @@
+//! ABC/XYZ-классификация товаров маркетплейсов по кварталам.
+//!
+//! ABC — доля товара в выручке квартала: товары сортируются по выручке, и пока
+//! накопленная доля предыдущих товаров меньше порога A (по умолчанию 80 %), товар
+//! получает A, до порога B (95 %) — B, остальные — C. XYZ — стабильность спроса:
+//! коэффициент вариации продаж в штуках по неделям квартала (X ≤ 25 %, Y ≤ 50 %,
+//! иначе Z). «CZ» — низкая выручка и нерегулярный спрос, т.е. кандидаты в неликвид.
+//!
+//! Источник — регистр продаж p900 (включая архив), ключ товара —
+//! `marketplace_product_ref` (a007). Классифицируются только товары с движением в
+//! квартале. Расчёт квартала (task027) целиком заменяет его строки в
+//! `a007_abc_xyz_classes`; списки и дашборды читают последний рассчитанный квартал.
+
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate};
+use contracts::domain::a007_marketplace_product::aggregate::{AbcClass, AbcXyzLabelDto, XyzClass};
+use sea_orm::{ConnectionTrait, Statement, TransactionTrait, Value};
+use std::collections::HashMap;
+
+use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+
+/// Недель в квартале: последние 6–8 дней квартала относятся к 13-й неделе.
+const WEEKS_IN_QUARTER: usize = 13;
+
+/// Пороги классов (настраиваются в конфиге task027), в процентах.
+#[derive(Debug, Clone, Copy)]
+pub struct AbcXyzThresholds {
+    /// Накопленная доля выручки, до которой товары относятся к A.
+    pub a_share: f64,
+    /// Накопленная доля выручки, до которой товары относятся к B.
+    pub b_share: f64,
+    /// Максимальный коэффициент вариации спроса для X.
+    pub x_max_cv: f64,
+    /// Максимальный коэффициент вариации спроса для Y.
+    pub y_max_cv: f64,
+}
+
+impl Default for AbcXyzThresholds {
+    fn default() -> Self {
+        Self {
+            a_share: 80.0,
+            b_share: 95.0,
+            x_max_cv: 25.0,
+            y_max_cv: 50.0,
+        }
+    }
+}
+
+/// Квартал: год и номер 1..=4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Quarter {
+    pub year: i32,
+    pub number: u32,
+}
+
+impl Quarter {
+    pub fn of(date: NaiveDate) -> Self {
+        Self {
+            year: date.year(),
+            number: (date.month() - 1) / 3 + 1,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        if self.number == 1 {
+            Self {
+                year: self.year - 1,
+                number: 4,
+            }
+        } else {
+            Self {
+                year: self.year,
+                number: self.number - 1,
+            }
+        }
+    }
+
+    /// Код квартала `YYYY-Qn` — сортируется так же, как кварталы.
+    pub fn code(self) -> String {
+        format!("{:04}-Q{}", self.year, self.number)
+    }
+
+    pub fn first_day(self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, (self.number - 1) * 3 + 1, 1)
+            .expect("valid quarter start")
+    }
+
+    /// Первый день следующего квартала (граница периода, не включается).
+    pub fn end_exclusive(self) -> NaiveDate {
+        if self.number == 4 {
+            NaiveDate::from_ymd_opt(self.year + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(self.year, self.number * 3 + 1, 1)
+        }
+        .expect("valid quarter end")
+    }
+}
+
+/// Последние `count` завершённых кварталов до `today`, от старых к новым.
+pub fn completed_quarters(today: NaiveDate, count: usize) -> Vec<Quarter> {
+    let mut quarters = Vec::with_capacity(count);
+    let mut quarter = Quarter::of(today).previous();
+    for _ in 0..count {
+        quarters.push(quarter);
+        quarter = quarter.previous();
+    }
+    quarters.reverse();
+    quarters
+}
+
+/// ABC-классы в порядке входных выручек. Класс определяется накопленной долей
+/// товаров с большей выручкой, поэтому лидер всегда A; без выручки — C.
+pub fn abc_classes(revenues: &[f64], thresholds: &AbcXyzThresholds) -> Vec<AbcClass> {
+    let total: f64 = revenues.iter().filter(|value| **value > 0.0).sum();
+    let mut order: Vec<usize> = (0..revenues.len()).collect();
+    order.sort_by(|a, b| revenues[*b].total_cmp(&revenues[*a]));
+
+    let mut classes = vec![AbcClass::C; revenues.len()];
+    let mut cumulative = 0.0;
+    for index in order {
+        let revenue = revenues[index];
+        if total <= 0.0 || revenue <= 0.0 {
+            continue;
+        }
+        let share_before = cumulative / total * 100.0;
+        classes[index] = if share_before < thresholds.a_share {
+            AbcClass::A
+        } else if share_before < thresholds.b_share {
+            AbcClass::B
+        } else {
+            AbcClass::C
+        };
+        cumulative += revenue;
+    }
+    classes
+}
+
+/// Коэффициент вариации (стандартное отклонение / среднее), %. None — спроса нет.
+pub fn demand_cv(weekly_qty: &[f64]) -> Option<f64> {
+    if weekly_qty.is_empty() {
+        return None;
+    }
+    let count = weekly_qty.len() as f64;
+    let mean = weekly_qty.iter().sum::<f64>() / count;
+    if mean <= 0.0 {
+        return None;
+    }
+    let variance = weekly_qty
+        .iter()
+        .map(|qty| (qty - mean).powi(2))
+        .sum::<f64>()
+        / count;
+    Some(variance.sqrt() / mean * 100.0)
+}
+
+pub fn xyz_class(cv: Option<f64>, thresholds: &AbcXyzThresholds) -> XyzClass {
+    match cv {
+        Some(cv) if cv <= thresholds.x_max_cv => XyzClass::X,
+        Some(cv) if cv <= thresholds.y_max_cv => XyzClass::Y,
+        _ => XyzClass::Z,
+    }
+}
+
+/// Рассчитанный класс товара за квартал.
+#[derive(Debug, Clone)]
+pub struct AbcXyzRow {
+    pub marketplace_product_ref: String,
+    pub abc: AbcClass,
+    pub xyz: XyzClass,
+    pub revenue: f64,
+    pub revenue_share: f64,
+    pub qty: f64,
+    pub demand_cv: Option<f64>,
+}
+
+#[derive(Default)]
+struct ProductAccum {
+    revenue: f64,
+    weekly_qty: [f64; WEEKS_IN_QUARTER],
+}
+
+/// Классифицировать товары за квартал по продажам p900.
+pub async fn compute_quarter(
+    quarter: Quarter,
+    thresholds: &AbcXyzThresholds,
+) -> Result<Vec<AbcXyzRow>> {
+    let db = get_connection();
+    let date_from = quarter.first_day().format("%Y-%m-%d").to_string();
+    let date_to = quarter.end_exclusive().format("%Y-%m-%d").to_string();
+    let sql = format!(
+        "SELECT marketplace_product_ref, \
+                CAST((julianday(substr(sale_date, 1, 10)) - julianday(?)) / 7 AS INTEGER) AS week_no, \
+                SUM(qty) AS qty, \
+                SUM(COALESCE(amount_line, 0)) AS revenue \
+         FROM {} p900 \
+         WHERE sale_date >= ? \
+           AND sale_date < ? \
+           AND marketplace_product_ref IS NOT NULL \
+           AND marketplace_product_ref <> '' \
+         GROUP BY marketplace_product_ref, week_no",
+        projection_archive::source("p900_sales_register", Some(&date_from))
+    );
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            [date_from.clone().into(), date_from.into(), date_to.into()],
+        ))
+        .await?;
+
+    let mut products: HashMap<String, ProductAccum> = HashMap::new();
+    for row in rows {
+        let product_ref: String = row.try_get("", "marketplace_product_ref")?;
+        let week_no: i64 = row.try_get("", "week_no").unwrap_or_default();
+        let week = (week_no.max(0) as usize).min(WEEKS_IN_QUARTER - 1);
+        let accum = products.entry(product_ref).or_default();
+        accum.revenue += row.try_get::<f64>("", "revenue").unwrap_or_default();
+        accum.weekly_qty[week] += row.try_get::<f64>("", "qty").unwrap_or_default();
+    }
+
+    let products: Vec<(String, ProductAccum)> = products.into_iter().collect();
+    let revenues: Vec<f64> = products.iter().map(|(_, accum)| accum.revenue).collect();
+    let total: f64 = revenues.iter().filter(|value| **value > 0.0).sum();
+    let classes = abc_classes(&revenues, thresholds);
+
+    Ok(products
+        .into_iter()
+        .zip(classes)
+        .map(|((product_ref, accum), abc)| {
+            // Возвраты уменьшают спрос недели, но не делают его отрицательным.
+            let weekly: Vec<f64> = accum.weekly_qty.iter().map(|qty| qty.max(0.0)).collect();
+            let cv = demand_cv(&weekly);
+            AbcXyzRow {
+                marketplace_product_ref: product_ref,
+                abc,
+                xyz: xyz_class(cv, thresholds),
+                revenue: accum.revenue,
+                revenue_share: if total > 0.0 {
+                    accum.revenue.max(0.0) / total * 100.0
+                } else {
+                    0.0
+                },
+                qty: weekly.iter().sum(),
+                demand_cv: cv,
+            }
+        })
+        .collect())
+}
+
+/// Заменить классы квартала новым расчётом.
+pub async fn replace_quarter(
+    quarter: Quarter,
+    rows: &[AbcXyzRow],
+    computed_at: &str,
+) -> Result<()> {
+    let db = get_connection();
+    let code = quarter.code();
+    let txn = db.begin().await?;
+    txn.execute(Statement::from_sql_and_values(
+        txn.get_database_backend(),
+        "DELETE FROM a007_abc_xyz_classes WHERE quarter = ?",
+        [code.clone().into()],
+    ))
+    .await?;
+    for row in rows {
+        txn.execute(Statement::from_sql_and_values(
+            txn.get_database_backend(),
+            "INSERT INTO a007_abc_xyz_classes (
+                marketplace_product_ref, quarter, abc_class, xyz_class,
+                revenue, revenue_share, qty, demand_cv, computed_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            [
+                row.marketplace_product_ref.clone().into(),
+                code.clone().into(),
+                row.abc.code().into(),
+                row.xyz.code().into(),
+                row.revenue.into(),
+                row.revenue_share.into(),
+                row.qty.into(),
+                Value::from(row.demand_cv),
+                computed_at.into(),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Последний рассчитанный квартал (`YYYY-Qn`), если расчёт уже был.
+pub async fn latest_quarter() -> Result<Option<String>> {
+    let db = get_connection();
+    let row = db
+        .query_one(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT MAX(quarter) AS quarter FROM a007_abc_xyz_classes",
+        ))
+        .await?;
+    Ok(row.and_then(|row| row.try_get::<Option<String>>("", "quarter").ok().flatten()))
+}
+
+/// SQL-условие «товар a007 с алиасом `alias` имеет класс в последнем квартале»
+/// и его параметры; `None`, если фильтр по классам не задан.
+pub fn class_filter_sql(
+    alias: &str,
+    abc: Option<AbcClass>,
+    xyz: Option<XyzClass>,
+) -> Option<(String, Vec<Value>)> {
+    if abc.is_none() && xyz.is_none() {
+        return None;
+    }
+    let mut sql = format!(
+        "EXISTS (SELECT 1 FROM a007_abc_xyz_classes abc_xyz \
+         WHERE abc_xyz.marketplace_product_ref = {alias}.id \
+           AND abc_xyz.quarter = (SELECT MAX(quarter) FROM a007_abc_xyz_classes)"
+    );
+    let mut values = Vec::new();
+    if let Some(abc) = abc {
+        sql.push_str(" AND abc_xyz.abc_class = ?");
+        values.push(abc.code().into());
+    }
+    if let Some(xyz) = xyz {
+        sql.push_str(" AND abc_xyz.xyz_class = ?");
+        values.push(xyz.code().into());
+    }
+    sql.push(')');
+    Some((sql, values))
+}
+
+/// Метки последнего квартала по id товаров a007.
+pub async fn labels_for_products(
+    product_refs: &[String],
+) -> Result<HashMap<String, AbcXyzLabelDto>> {
+    load_labels("c.marketplace_product_ref", product_refs).await
+}
+
+/// Метки последнего квартала по SKU маркетплейса (для WB — nm_id).
+pub async fn labels_for_skus(skus: &[String]) -> Result<HashMap<String, AbcXyzLabelDto>> {
+    load_labels("p.marketplace_sku", skus).await
+}
+
+async fn load_labels(key_column: &str, keys: &[String]) -> Result<HashMap<String, AbcXyzLabelDto>> {
+    if keys.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let db = get_connection();
+    let placeholders = vec!["?"; keys.len()].join(", ");
+    let sql = format!(
+        "SELECT {key_column} AS label_key, c.quarter, c.abc_class, c.xyz_class, \
+                c.revenue, c.revenue_share, c.demand_cv \
+         FROM a007_abc_xyz_classes c \
+         JOIN a007_marketplace_product p ON p.id = c.marketplace_product_ref \
+         WHERE c.quarter = (SELECT MAX(quarter) FROM a007_abc_xyz_classes) \
+           AND {key_column} IN ({placeholders})"
+    );
+    let values: Vec<Value> = keys.iter().map(|key| key.clone().into()).collect();
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let key: String = row.try_get("", "label_key").ok()?;
+            let abc = AbcClass::from_code(&row.try_get::<String>("", "abc_class").ok()?)?;
+            let xyz = XyzClass::from_code(&row.try_get::<String>("", "xyz_class").ok()?)?;
+            Some((
+                key,
+                AbcXyzLabelDto {
+                    quarter: row.try_get("", "quarter").unwrap_or_default(),
+                    abc,
+                    xyz,
+                    revenue: row.try_get("", "revenue").unwrap_or_default(),
+                    revenue_share: row.try_get("", "revenue_share").unwrap_or_default(),
+                    demand_cv: row.try_get("", "demand_cv").ok().flatten(),
+                },
+            ))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn abc_follows_cumulative_revenue_share() {
+        // Доли: 50, 25, 15, 6, 4 % — A до 80 %, B до 95 %.
+        let revenues = [250.0, 125.0, 75.0, 30.0, 20.0];
+        let classes = abc_classes(&revenues, &AbcXyzThresholds::default());
+        assert_eq!(
+            classes,
+            vec![
+                AbcClass::A,
+                AbcClass::A,
+                AbcClass::A,
+                AbcClass::B,
+                AbcClass::C
+            ]
+        );
+    }
+
+    #[test]
+    fn abc_keeps_input_order_and_puts_non_positive_revenue_in_c() {
+        let revenues = [0.0, 100.0, -10.0];
+        assert_eq!(
+            abc_classes(&revenues, &AbcXyzThresholds::default()),
+            vec![AbcClass::C, AbcClass::A, AbcClass::C]
+        );
+    }
+
+    #[test]
+    fn xyz_by_weekly_demand_variation() {
+        let thresholds = AbcXyzThresholds::default();
+        assert_eq!(xyz_class(demand_cv(&[10.0; 13]), &thresholds), XyzClass::X);
+
+        let mut spiky = [0.0; 13];
+        spiky[4] = 13.0;
+        let cv = demand_cv(&spiky).unwrap();
+        assert!(cv > 300.0);
+        assert_eq!(xyz_class(Some(cv), &thresholds), XyzClass::Z);
+
+        assert_eq!(demand_cv(&[0.0; 13]), None);
+        assert_eq!(xyz_class(None, &thresholds), XyzClass::Z);
+    }
+
+    #[test]
+    fn completed_quarters_skip_current_and_cross_year() {
+        let quarters = completed_quarters(date("2026-02-10"), 2);
+        assert_eq!(
+            quarters.iter().map(|q| q.code()).collect::<Vec<_>>(),
+            vec!["2025-Q3", "2025-Q4"]
+        );
+        assert_eq!(quarters[1].first_day(), date("2025-10-01"));
+        assert_eq!(quarters[1].end_exclusive(), date("2026-01-01"));
+    }
+}
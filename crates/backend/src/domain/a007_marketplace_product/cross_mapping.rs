@@ -0,0 +1,372 @@
+//! Сопоставление товаров маркетплейсов между собой через номенклатуру.
+//!
+//! Товары WB, Ozon и других маркетплейсов (a007), ссылающиеся на одну
+//! номенклатуру a004, собираются в группу — по ней сквозная аналитика считает
+//! товар один раз. Конфликт — штрихкод, который разные источники (товары a007 и
+//! записи p901 из 1С и маркетплейсов) относят к разным номенклатурам: такие
+//! товары задваиваются или попадают не в ту группу. Разрешение конфликта
+//! перепривязывает товары a007 и маркетплейсные записи p901 со штрихкодом на
+//! выбранную номенклатуру; записи 1С — мастер-данные и правятся только в 1С.
+
+use anyhow::Result;
+use contracts::domain::a007_marketplace_product::aggregate::{
+    BarcodeClaimDto, BarcodeConflictDto, CrossMappingGroupDto, CrossMappingProductDto,
+    CrossMappingResponse, ResolveBarcodeConflictResponse,
+};
+use sea_orm::{ConnectionTrait, Statement, TransactionTrait};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::shared::data::db::get_connection;
+
+/// Источник p901, который не перезаписывается при разрешении конфликтов.
+const MASTER_BARCODE_SOURCE: &str = "1C";
+
+/// Запись «штрихкод → номенклатура» из p901.
+#[derive(Debug, Clone)]
+pub struct BarcodeClaim {
+    pub barcode: String,
+    pub source: String,
+    pub nomenclature_ref: String,
+}
+
+/// Наименование номенклатуры: (артикул, название).
+type NomenclatureNames = HashMap<String, (String, String)>;
+
+fn named_group(
+    nomenclature_ref: String,
+    products: Vec<CrossMappingProductDto>,
+    names: &NomenclatureNames,
+) -> CrossMappingGroupDto {
+    let marketplace_codes: BTreeSet<String> = products
+        .iter()
+        .map(|product| product.marketplace_code.clone())
+        .collect();
+    let name = names.get(&nomenclature_ref);
+    CrossMappingGroupDto {
+        nomenclature_article: name.map(|(article, _)| article.clone()),
+        nomenclature_name: name.map(|(_, description)| description.clone()),
+        nomenclature_ref,
+        marketplace_codes: marketplace_codes.into_iter().collect(),
+        products,
+    }
+}
+
+/// Сгруппировать сопоставленные товары по номенклатуре. Группы с товарами
+/// нескольких маркетплейсов идут первыми.
+pub fn build_groups(
+    products: &[CrossMappingProductDto],
+    names: &NomenclatureNames,
+) -> Vec<CrossMappingGroupDto> {
+    let mut by_nomenclature: BTreeMap<String, Vec<CrossMappingProductDto>> = BTreeMap::new();
+    for product in products {
+        if let Some(nomenclature_ref) = &product.nomenclature_ref {
+            by_nomenclature
+                .entry(nomenclature_ref.clone())
+                .or_default()
+                .push(product.clone());
+        }
+    }
+    let mut groups: Vec<CrossMappingGroupDto> = by_nomenclature
+        .into_iter()
+        .map(|(nomenclature_ref, products)| named_group(nomenclature_ref, products, names))
+        .collect();
+    groups.sort_by(|a, b| {
+        b.is_cross_marketplace()
+            .cmp(&a.is_cross_marketplace())
+            .then_with(|| a.nomenclature_article.cmp(&b.nomenclature_article))
+    });
+    groups
+}
+
+/// Найти штрихкоды, которые источники относят к разным номенклатурам.
+pub fn detect_conflicts(
+    products: &[CrossMappingProductDto],
+    claims: &[BarcodeClaim],
+    names: &NomenclatureNames,
+) -> Vec<BarcodeConflictDto> {
+    // штрихкод → номенклатура → источники
+    let mut by_barcode: BTreeMap<String, BTreeMap<String, BTreeSet<String>>> = BTreeMap::new();
+    for claim in claims {
+        let barcode = claim.barcode.trim();
+        if barcode.is_empty() {
+            continue;
+        }
+        by_barcode
+            .entry(barcode.to_string())
+            .or_default()
+            .entry(claim.nomenclature_ref.clone())
+            .or_default()
+            .insert(claim.source.clone());
+    }
+    let mut products_by_barcode: HashMap<String, Vec<CrossMappingProductDto>> = HashMap::new();
+    for product in products {
+        let Some(barcode) = product.barcode.as_deref().map(str::trim) else {
+            continue;
+        };
+        if barcode.is_empty() {
+            continue;
+        }
+        if let Some(nomenclature_ref) = &product.nomenclature_ref {
+            by_barcode
+                .entry(barcode.to_string())
+                .or_default()
+                .entry(nomenclature_ref.clone())
+                .or_default()
+                .insert(product.marketplace_code.clone());
+        }
+        products_by_barcode
+            .entry(barcode.to_string())
+            .or_default()
+            .push(product.clone());
+    }
+
+    by_barcode
+        .into_iter()
+        .filter(|(_, nomenclatures)| nomenclatures.len() > 1)
+        .map(|(barcode, nomenclatures)| {
+            let claims = nomenclatures
+                .into_iter()
+                .map(|(nomenclature_ref, sources)| {
+                    let name = names.get(&nomenclature_ref);
+                    BarcodeClaimDto {
+                        nomenclature_article: name.map(|(article, _)| article.clone()),
+                        nomenclature_name: name.map(|(_, description)| description.clone()),
+                        nomenclature_ref,
+                        sources: sources.into_iter().collect(),
+                    }
+                })
+                .collect();
+            BarcodeConflictDto {
+                products: products_by_barcode.remove(&barcode).unwrap_or_default(),
+                barcode,
+                claims,
+            }
+        })
+        .collect()
+}
+
+async fn load_products() -> Result<Vec<CrossMappingProductDto>> {
+    let db = get_connection();
+    let rows = db
+        .query_all(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT p.id, p.connection_mp_ref, p.marketplace_sku, p.article, p.barcode, \
+                    p.description, p.nomenclature_ref, \
+                    COALESCE(mp.marketplace_type, '') AS marketplace_code \
+             FROM a007_marketplace_product p \
+             LEFT JOIN a005_marketplace mp ON mp.id = p.marketplace_ref \
+             WHERE p.is_deleted = 0"
+                .to_string(),
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| CrossMappingProductDto {
+            id: row.try_get("", "id").unwrap_or_default(),
+            marketplace_code: row.try_get("", "marketplace_code").unwrap_or_default(),
+            connection_mp_ref: row.try_get("", "connection_mp_ref").unwrap_or_default(),
+            marketplace_sku: row.try_get("", "marketplace_sku").unwrap_or_default(),
+            article: row.try_get("", "article").unwrap_or_default(),
+            barcode: row.try_get("", "barcode").ok().flatten(),
+            description: row.try_get("", "description").unwrap_or_default(),
+            nomenclature_ref: row
+                .try_get::<Option<String>>("", "nomenclature_ref")
+                .ok()
+                .flatten()
+                .filter(|r| !r.is_empty()),
+        })
+        .collect())
+}
+
+async fn load_claims() -> Result<Vec<BarcodeClaim>> {
+    let db = get_connection();
+    let rows = db
+        .query_all(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT barcode, source, nomenclature_ref \
+             FROM p901_nomenclature_barcodes \
+             WHERE is_active = 1 AND nomenclature_ref IS NOT NULL AND nomenclature_ref <> ''"
+                .to_string(),
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(BarcodeClaim {
+                barcode: row.try_get("", "barcode").ok()?,
+                source: row.try_get("", "source").ok()?,
+                nomenclature_ref: row.try_get("", "nomenclature_ref").ok()?,
+            })
+        })
+        .collect())
+}
+
+async fn load_nomenclature_names() -> Result<NomenclatureNames> {
+    let db = get_connection();
+    let rows = db
+        .query_all(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT id, article, description FROM a004_nomenclature WHERE is_deleted = 0"
+                .to_string(),
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let id: String = row.try_get("", "id").ok()?;
+            let article: String = row.try_get("", "article").unwrap_or_default();
+            let description: String = row.try_get("", "description").unwrap_or_default();
+            Some((id, (article, description)))
+        })
+        .collect())
+}
+
+/// Группы по номенклатуре и конфликты штрихкодов.
+pub async fn overview() -> Result<CrossMappingResponse> {
+    let products = load_products().await?;
+    let claims = load_claims().await?;
+    let names = load_nomenclature_names().await?;
+
+    Ok(CrossMappingResponse {
+        groups: build_groups(&products, &names),
+        conflicts: detect_conflicts(&products, &claims, &names),
+        unmapped_count: products
+            .iter()
+            .filter(|product| product.nomenclature_ref.is_none())
+            .count(),
+    })
+}
+
+/// Привязать все товары и маркетплейсные штрихкоды p901 с `barcode` к
+/// `nomenclature_ref`. Возвращает None, если номенклатура не найдена.
+pub async fn resolve_conflict(
+    barcode: &str,
+    nomenclature_ref: &str,
+) -> Result<Option<ResolveBarcodeConflictResponse>> {
+    let db = get_connection();
+    let exists = db
+        .query_one(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "SELECT 1 AS found FROM a004_nomenclature WHERE id = ? AND is_deleted = 0",
+            [nomenclature_ref.into()],
+        ))
+        .await?
+        .is_some();
+    if !exists {
+        return Ok(None);
+    }
+
+    let now = chrono::Utc::now();
+    let txn = db.begin().await?;
+    let products = txn
+        .execute(Statement::from_sql_and_values(
+            txn.get_database_backend(),
+            "UPDATE a007_marketplace_product \
+             SET nomenclature_ref = ?, updated_at = ?, version = version + 1 \
+             WHERE TRIM(barcode) = ? AND is_deleted = 0 \
+               AND (nomenclature_ref IS NULL OR nomenclature_ref <> ?)",
+            [
+                nomenclature_ref.into(),
+                now.into(),
+                barcode.into(),
+                nomenclature_ref.into(),
+            ],
+        ))
+        .await?;
+    let barcodes = txn
+        .execute(Statement::from_sql_and_values(
+            txn.get_database_backend(),
+            "UPDATE p901_nomenclature_barcodes \
+             SET nomenclature_ref = ?, updated_at = ? \
+             WHERE barcode = ? AND source <> ? AND is_active = 1 \
+               AND (nomenclature_ref IS NULL OR nomenclature_ref <> ?)",
+            [
+                nomenclature_ref.into(),
+                now.to_rfc3339().into(),
+                barcode.into(),
+                MASTER_BARCODE_SOURCE.into(),
+                nomenclature_ref.into(),
+            ],
+        ))
+        .await?;
+    txn.commit().await?;
+
+    Ok(Some(ResolveBarcodeConflictResponse {
+        products_updated: products.rows_affected(),
+        barcodes_updated: barcodes.rows_affected(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product(
+        id: &str,
+        code: &str,
+        barcode: &str,
+        nomenclature: Option<&str>,
+    ) -> CrossMappingProductDto {
+        CrossMappingProductDto {
+            id: id.to_string(),
+            marketplace_code: code.to_string(),
+            connection_mp_ref: "conn".to_string(),
+            marketplace_sku: id.to_string(),
+            article: id.to_string(),
+            barcode: Some(barcode.to_string()),
+            description: String::new(),
+            nomenclature_ref: nomenclature.map(str::to_string),
+        }
+    }
+
+    fn claim(barcode: &str, source: &str, nomenclature: &str) -> BarcodeClaim {
+        BarcodeClaim {
+            barcode: barcode.to_string(),
+            source: source.to_string(),
+            nomenclature_ref: nomenclature.to_string(),
+        }
+    }
+
+    #[test]
+    fn groups_products_by_nomenclature_cross_marketplace_first() {
+        let products = vec![
+            product("wb-1", "mp-wb", "111", Some("n1")),
+            product("oz-1", "mp-ozon", "111", Some("n1")),
+            product("wb-2", "mp-wb", "222", Some("n2")),
+            product("wb-3", "mp-wb", "333", None),
+        ];
+        let groups = build_groups(&products, &HashMap::new());
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].nomenclature_ref, "n1");
+        assert!(groups[0].is_cross_marketplace());
+        assert_eq!(groups[0].marketplace_codes, vec!["mp-ozon", "mp-wb"]);
+        assert!(!groups[1].is_cross_marketplace());
+    }
+
+    #[test]
+    fn barcode_claimed_by_two_nomenclatures_is_a_conflict() {
+        let products = vec![
+            product("wb-1", "mp-wb", "111", Some("n1")),
+            product("oz-1", "mp-ozon", " 111 ", Some("n2")),
+            product("wb-2", "mp-wb", "222", Some("n3")),
+        ];
+        let claims = vec![claim("111", "1C", "n1"), claim("222", "1C", "n3")];
+        let conflicts = detect_conflicts(&products, &claims, &HashMap::new());
+
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.barcode, "111");
+        assert_eq!(conflict.products.len(), 2);
+        assert_eq!(conflict.claims[0].nomenclature_ref, "n1");
+        assert_eq!(conflict.claims[0].sources, vec!["1C", "mp-wb"]);
+        assert_eq!(conflict.claims[1].sources, vec!["mp-ozon"]);
+    }
+
+    #[test]
+    fn consistent_sources_are_not_a_conflict() {
+        let products = vec![product("wb-1", "mp-wb", "111", Some("n1"))];
+        let claims = vec![claim("111", "1C", "n1"), claim("111", "WB", "n1")];
+        assert!(detect_conflicts(&products, &claims, &HashMap::new()).is_empty());
+    }
+}
@@ -0,0 +1,567 @@
+//! Недельный прогноз спроса по товарам маркетплейсов (task029).
+//!
+//! История — продажи p900 (включая архив) в штуках по неделям с понедельника, от
+//! первой недели с продажами товара до последней завершённой. Уровень спроса —
+//! экспоненциальное сглаживание; если истории не меньше года, ряд предварительно
+//! очищается от сезонности помесячными коэффициентами, а прогноз недели умножается
+//! на коэффициент её месяца. Для коротких рядов — простое среднее.
+//!
+//! Каждый запуск сохраняется в `a007_demand_forecasts` с датой расчёта `made_on`
+//! (понедельник недели запуска): повторный запуск в ту же неделю заменяет её прогноз,
+//! прошлые прогнозы остаются для сравнения с фактом (D412). Планирование пополнения
+//! берёт последний расчёт через [`latest_forecast`].
+
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate};
+use contracts::dashboards::d412_demand_forecast::ForecastMethod;
+use sea_orm::{ConnectionTrait, Statement, TransactionTrait, Value};
+use std::collections::HashMap;
+
+use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+
+/// Параметры прогноза (настраиваются в конфиге task029).
+#[derive(Debug, Clone, Copy)]
+pub struct ForecastParams {
+    /// Коэффициент сглаживания уровня, 0..1: больше — быстрее реакция на последние недели.
+    pub alpha: f64,
+    /// Сколько недель истории нужно для сглаживания (меньше — среднее).
+    pub min_smoothing_weeks: usize,
+    /// Сколько недель истории нужно для сезонных коэффициентов.
+    pub min_seasonal_weeks: usize,
+}
+
+impl Default for ForecastParams {
+    fn default() -> Self {
+        Self {
+            alpha: 0.3,
+            min_smoothing_weeks: 8,
+            min_seasonal_weeks: 52,
+        }
+    }
+}
+
+/// Понедельник недели, в которую попадает `date`.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Сезонные коэффициенты по месяцам (индекс 0 — январь): средние продажи недель
+/// месяца к среднему по всей истории. Месяц без недель в истории — 1.0.
+pub fn monthly_indices(history: &[(NaiveDate, f64)]) -> [f64; 12] {
+    let mut indices = [1.0; 12];
+    if history.is_empty() {
+        return indices;
+    }
+    let overall = history.iter().map(|(_, qty)| qty).sum::<f64>() / history.len() as f64;
+    if overall <= 0.0 {
+        return indices;
+    }
+    let mut sums = [0.0; 12];
+    let mut counts = [0usize; 12];
+    for (week, qty) in history {
+        let month = week.month0() as usize;
+        sums[month] += qty;
+        counts[month] += 1;
+    }
+    for month in 0..12 {
+        if counts[month] > 0 {
+            // Ограничение не даёт месяцу без продаж обнулить прогноз навсегда.
+            indices[month] = (sums[month] / counts[month] as f64 / overall).clamp(0.2, 5.0);
+        }
+    }
+    indices
+}
+
+/// Уровень ряда простым экспоненциальным сглаживанием.
+pub fn smoothed_level(values: &[f64], alpha: f64) -> f64 {
+    let alpha = alpha.clamp(0.01, 1.0);
+    let mut iter = values.iter();
+    let Some(first) = iter.next() else {
+        return 0.0;
+    };
+    iter.fold(*first, |level, value| alpha * value + (1.0 - alpha) * level)
+}
+
+/// Прогноз на `horizon` недель начиная с `first_week` по плотной недельной истории
+/// (все недели подряд, без пропусков).
+pub fn forecast(
+    history: &[(NaiveDate, f64)],
+    first_week: NaiveDate,
+    horizon: usize,
+    params: &ForecastParams,
+) -> Option<(ForecastMethod, Vec<(NaiveDate, f64)>)> {
+    if history.is_empty() || horizon == 0 {
+        return None;
+    }
+    let history: Vec<(NaiveDate, f64)> = history
+        .iter()
+        .map(|(week, qty)| (*week, qty.max(0.0)))
+        .collect();
+    let weeks = (0..horizon).map(|offset| first_week + Duration::weeks(offset as i64));
+
+    if history.len() < params.min_smoothing_weeks {
+        let mean = history.iter().map(|(_, qty)| qty).sum::<f64>() / history.len() as f64;
+        return Some((
+            ForecastMethod::MovingAverage,
+            weeks.map(|week| (week, mean)).collect(),
+        ));
+    }
+
+    let seasonal = history.len() >= params.min_seasonal_weeks;
+    let indices = if seasonal {
+        monthly_indices(&history)
+    } else {
+        [1.0; 12]
+    };
+    let deseasonalized: Vec<f64> = history
+        .iter()
+        .map(|(week, qty)| qty / indices[week.month0() as usize])
+        .collect();
+    let level = smoothed_level(&deseasonalized, params.alpha);
+    let method = if seasonal {
+        ForecastMethod::SeasonalSmoothing
+    } else {
+        ForecastMethod::ExponentialSmoothing
+    };
+    Some((
+        method,
+        weeks
+            .map(|week| (week, level * indices[week.month0() as usize]))
+            .collect(),
+    ))
+}
+
+/// Прогноз товара на одну неделю.
+#[derive(Debug, Clone)]
+pub struct ForecastRow {
+    pub marketplace_product_ref: String,
+    pub week_start: NaiveDate,
+    pub forecast_qty: f64,
+    pub method: ForecastMethod,
+}
+
+/// Рассчитать прогноз всех товаров: история — `history_weeks` завершённых недель
+/// до `made_on` (понедельник), прогноз — `horizon` недель начиная с `made_on`.
+pub async fn compute(
+    made_on: NaiveDate,
+    history_weeks: i64,
+    horizon: usize,
+    params: &ForecastParams,
+) -> Result<Vec<ForecastRow>> {
+    let db = get_connection();
+    let history_from = made_on - Duration::weeks(history_weeks.max(1));
+    let date_from = history_from.format("%Y-%m-%d").to_string();
+    let date_to = made_on.format("%Y-%m-%d").to_string();
+    let sql = format!(
+        "SELECT marketplace_product_ref, \
+                date(substr(sale_date, 1, 10), 'weekday 0', '-6 days') AS week_start, \
+                SUM(qty) AS qty \
+         FROM {} p900 \
+         WHERE sale_date >= ? \
+           AND sale_date < ? \
+           AND marketplace_product_ref IS NOT NULL \
+           AND marketplace_product_ref <> '' \
+         GROUP BY marketplace_product_ref, week_start",
+        projection_archive::source("p900_sales_register", Some(&date_from))
+    );
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            [date_from.into(), date_to.into()],
+        ))
+        .await?;
+
+    let mut weekly: HashMap<String, HashMap<NaiveDate, f64>> = HashMap::new();
+    for row in rows {
+        let product_ref: String = row.try_get("", "marketplace_product_ref")?;
+        let week: String = row.try_get("", "week_start").unwrap_or_default();
+        let Ok(week) = NaiveDate::parse_from_str(&week, "%Y-%m-%d") else {
+            continue;
+        };
+        *weekly
+            .entry(product_ref)
+            .or_default()
+            .entry(week)
+            .or_default() += row.try_get::<f64>("", "qty").unwrap_or_default();
+    }
+
+    let last_week = made_on - Duration::weeks(1);
+    let mut forecasts = Vec::new();
+    for (product_ref, weeks) in weekly {
+        let Some(first) = weeks.keys().min().copied() else {
+            continue;
+        };
+        // Плотный ряд: недели без продаж после первой продажи — нули.
+        let mut history = Vec::new();
+        let mut week = first;
+        while week <= last_week {
+            history.push((week, weeks.get(&week).copied().unwrap_or_default()));
+            week += Duration::weeks(1);
+        }
+        if let Some((method, points)) = forecast(&history, made_on, horizon, params) {
+            forecasts.extend(
+                points
+                    .into_iter()
+                    .map(|(week_start, forecast_qty)| ForecastRow {
+                        marketplace_product_ref: product_ref.clone(),
+                        week_start,
+                        forecast_qty,
+                        method,
+                    }),
+            );
+        }
+    }
+    Ok(forecasts)
+}
+
+/// Заменить прогноз расчёта `made_on`.
+pub async fn replace_run(
+    made_on: NaiveDate,
+    rows: &[ForecastRow],
+    computed_at: &str,
+) -> Result<()> {
+    let db = get_connection();
+    let made_on = made_on.format("%Y-%m-%d").to_string();
+    let txn = db.begin().await?;
+    txn.execute(Statement::from_sql_and_values(
+        txn.get_database_backend(),
+        "DELETE FROM a007_demand_forecasts WHERE made_on = ?",
+        [made_on.clone().into()],
+    ))
+    .await?;
+    for row in rows {
+        txn.execute(Statement::from_sql_and_values(
+            txn.get_database_backend(),
+            "INSERT INTO a007_demand_forecasts (
+                marketplace_product_ref, made_on, week_start, forecast_qty, method, computed_at
+            ) VALUES (?, ?, ?, ?, ?, ?)",
+            [
+                row.marketplace_product_ref.clone().into(),
+                made_on.clone().into(),
+                row.week_start.format("%Y-%m-%d").to_string().into(),
+                row.forecast_qty.into(),
+                row.method.code().into(),
+                computed_at.into(),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Отбор товаров для чтения прогнозов и факта.
+#[derive(Debug, Clone, Default)]
+pub struct ForecastFilter {
+    pub connection_mp_ref: Option<String>,
+    pub marketplace_product_ref: Option<String>,
+    /// Активная организация: товары кабинетов только этой организации.
+    pub organization_ref: Option<String>,
+}
+
+impl ForecastFilter {
+    /// Условие на товар с алиасом колонки ссылки `column` и его параметры.
+    fn sql(&self, column: &str) -> (String, Vec<Value>) {
+        let mut sql = String::new();
+        let mut values = Vec::new();
+        if let Some(product_ref) = self.marketplace_product_ref.as_deref() {
+            sql.push_str(&format!(" AND {column} = ?"));
+            values.push(product_ref.into());
+        }
+        if let Some(connection_mp_ref) = self.connection_mp_ref.as_deref() {
+            sql.push_str(&format!(
+                " AND {column} IN (SELECT id FROM a007_marketplace_product WHERE connection_mp_ref = ?)"
+            ));
+            values.push(connection_mp_ref.into());
+        }
+        if let Some(organization_ref) = self.organization_ref.as_deref() {
+            sql.push_str(&format!(
+                " AND {column} IN (SELECT p.id FROM a007_marketplace_product p \
+                 JOIN a006_connection_mp c ON c.id = p.connection_mp_ref \
+                 WHERE LOWER(TRIM(REPLACE(COALESCE(c.organization_ref, ''), '\"', ''))) = LOWER(TRIM(REPLACE(?, '\"', ''))))"
+            ));
+            values.push(organization_ref.into());
+        }
+        (sql, values)
+    }
+}
+
+/// Продажи по товарам и неделям: `(товар, понедельник, шт.)`.
+pub async fn weekly_actuals(
+    filter: &ForecastFilter,
+    week_from: &str,
+    week_to_exclusive: &str,
+) -> Result<Vec<(String, String, f64)>> {
+    let db = get_connection();
+    let (condition, mut values) = filter.sql("marketplace_product_ref");
+    let sql = format!(
+        "SELECT marketplace_product_ref, \
+                date(substr(sale_date, 1, 10), 'weekday 0', '-6 days') AS week_start, \
+                SUM(qty) AS qty \
+         FROM {} p900 \
+         WHERE sale_date >= ? \
+           AND sale_date < ? \
+           AND marketplace_product_ref IS NOT NULL \
+           AND marketplace_product_ref <> ''{condition} \
+         GROUP BY marketplace_product_ref, week_start",
+        projection_archive::source("p900_sales_register", Some(week_from))
+    );
+    values.insert(0, week_to_exclusive.into());
+    values.insert(0, week_from.into());
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some((
+                row.try_get("", "marketplace_product_ref").ok()?,
+                row.try_get("", "week_start").ok()?,
+                row.try_get("", "qty").unwrap_or_default(),
+            ))
+        })
+        .collect())
+}
+
+/// Прогнозы недель `week_from..week_to_exclusive`, сделанные ровно за
+/// `lead_weeks` недель до них: `(товар, понедельник, шт.)`.
+pub async fn forecasts_at_lead(
+    filter: &ForecastFilter,
+    week_from: &str,
+    week_to_exclusive: &str,
+    lead_weeks: u32,
+) -> Result<Vec<(String, String, f64)>> {
+    let db = get_connection();
+    let (condition, filter_values) = filter.sql("marketplace_product_ref");
+    let sql = format!(
+        "SELECT marketplace_product_ref, week_start, forecast_qty \
+         FROM a007_demand_forecasts \
+         WHERE week_start >= ? \
+           AND week_start < ? \
+           AND made_on = date(week_start, ?){condition}"
+    );
+    let mut values: Vec<Value> = vec![
+        week_from.into(),
+        week_to_exclusive.into(),
+        format!("-{} days", lead_weeks * 7).into(),
+    ];
+    values.extend(filter_values);
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some((
+                row.try_get("", "marketplace_product_ref").ok()?,
+                row.try_get("", "week_start").ok()?,
+                row.try_get("", "forecast_qty").unwrap_or_default(),
+            ))
+        })
+        .collect())
+}
+
+/// Последний расчёт прогноза: дата `made_on` и строки `(товар, понедельник, шт., метод)`.
+pub async fn latest_run(
+    filter: &ForecastFilter,
+) -> Result<Option<(String, Vec<(String, String, f64, Option<ForecastMethod>)>)>> {
+    let db = get_connection();
+    let made_on = db
+        .query_one(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT MAX(made_on) AS made_on FROM a007_demand_forecasts",
+        ))
+        .await?
+        .and_then(|row| row.try_get::<Option<String>>("", "made_on").ok().flatten());
+    let Some(made_on) = made_on else {
+        return Ok(None);
+    };
+
+    let (condition, filter_values) = filter.sql("marketplace_product_ref");
+    let sql = format!(
+        "SELECT marketplace_product_ref, week_start, forecast_qty, method \
+         FROM a007_demand_forecasts \
+         WHERE made_on = ?{condition} \
+         ORDER BY week_start"
+    );
+    let mut values: Vec<Value> = vec![made_on.clone().into()];
+    values.extend(filter_values);
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+    let rows = rows
+        .into_iter()
+        .filter_map(|row| {
+            Some((
+                row.try_get("", "marketplace_product_ref").ok()?,
+                row.try_get("", "week_start").ok()?,
+                row.try_get("", "forecast_qty").unwrap_or_default(),
+                row.try_get::<String>("", "method")
+                    .ok()
+                    .and_then(|code| ForecastMethod::from_code(&code)),
+            ))
+        })
+        .collect();
+    Ok(Some((made_on, rows)))
+}
+
+/// Прогноз спроса товаров на ближайшие `weeks` недель последнего расчёта, шт.
+/// Точка входа для планирования пополнения.
+pub async fn latest_forecast(
+    product_refs: &[String],
+    weeks: usize,
+) -> Result<HashMap<String, f64>> {
+    if product_refs.is_empty() || weeks == 0 {
+        return Ok(HashMap::new());
+    }
+    let db = get_connection();
+    let placeholders = vec!["?"; product_refs.len()].join(", ");
+    let sql = format!(
+        "SELECT marketplace_product_ref, SUM(forecast_qty) AS qty \
+         FROM a007_demand_forecasts \
+         WHERE made_on = (SELECT MAX(made_on) FROM a007_demand_forecasts) \
+           AND week_start < date(made_on, ?) \
+           AND marketplace_product_ref IN ({placeholders}) \
+         GROUP BY marketplace_product_ref"
+    );
+    let mut values: Vec<Value> = vec![format!("+{} days", weeks * 7).into()];
+    values.extend(
+        product_refs
+            .iter()
+            .map(|product_ref| product_ref.clone().into()),
+    );
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some((
+                row.try_get("", "marketplace_product_ref").ok()?,
+                row.try_get("", "qty").unwrap_or_default(),
+            ))
+        })
+        .collect())
+}
+
+/// Артикул и наименование товаров a007 по ссылкам.
+pub async fn product_labels(product_refs: &[String]) -> Result<HashMap<String, (String, String)>> {
+    if product_refs.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let db = get_connection();
+    let placeholders = vec!["?"; product_refs.len()].join(", ");
+    let sql = format!(
+        "SELECT id, COALESCE(article, '') AS article, COALESCE(description, '') AS product_name \
+         FROM a007_marketplace_product \
+         WHERE id IN ({placeholders})"
+    );
+    let values: Vec<Value> = product_refs
+        .iter()
+        .map(|product_ref| product_ref.clone().into())
+        .collect();
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some((
+                row.try_get("", "id").ok()?,
+                (
+                    row.try_get("", "article").unwrap_or_default(),
+                    row.try_get("", "product_name").unwrap_or_default(),
+                ),
+            ))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn weekly(first: &str, values: &[f64]) -> Vec<(NaiveDate, f64)> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, qty)| (date(first) + Duration::weeks(index as i64), *qty))
+            .collect()
+    }
+
+    #[test]
+    fn week_starts_on_monday() {
+        assert_eq!(week_start(date("2026-10-18")), date("2026-10-12"));
+        assert_eq!(week_start(date("2026-10-12")), date("2026-10-12"));
+    }
+
+    #[test]
+    fn short_history_uses_average() {
+        let history = weekly("2026-09-07", &[4.0, 6.0, 5.0]);
+        let (method, points) =
+            forecast(&history, date("2026-09-28"), 2, &ForecastParams::default()).unwrap();
+        assert_eq!(method, ForecastMethod::MovingAverage);
+        assert_eq!(
+            points,
+            vec![(date("2026-09-28"), 5.0), (date("2026-10-05"), 5.0)]
+        );
+    }
+
+    #[test]
+    fn smoothing_follows_recent_level() {
+        // Спрос вырос с 10 до 20 шт. в неделю: прогноз ближе к новому уровню.
+        let mut values = vec![10.0; 8];
+        values.extend([20.0; 8]);
+        let history = weekly("2026-06-01", &values);
+        let (method, points) =
+            forecast(&history, date("2026-09-21"), 1, &ForecastParams::default()).unwrap();
+        assert_eq!(method, ForecastMethod::ExponentialSmoothing);
+        assert!(points[0].1 > 19.0 && points[0].1 < 20.0);
+    }
+
+    #[test]
+    fn seasonal_indices_scale_forecast_by_month() {
+        // Два года: декабрь продаёт втрое больше остальных месяцев.
+        let first = date("2024-01-01");
+        let history: Vec<(NaiveDate, f64)> = (0..104)
+            .map(|index| {
+                let week = first + Duration::weeks(index);
+                (week, if week.month() == 12 { 30.0 } else { 10.0 })
+            })
+            .collect();
+        let indices = monthly_indices(&history);
+        assert!(indices[11] > 2.0 * indices[5]);
+
+        let params = ForecastParams::default();
+        let (method, december) = forecast(&history, date("2025-12-01"), 1, &params).unwrap();
+        assert_eq!(method, ForecastMethod::SeasonalSmoothing);
+        let (_, june) = forecast(&history, date("2025-06-02"), 1, &params).unwrap();
+        assert!(december[0].1 > 2.5 * june[0].1);
+    }
+}
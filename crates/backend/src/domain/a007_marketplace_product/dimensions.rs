@@ -0,0 +1,90 @@
+//! Габариты товаров маркетплейсов из карточек WB (`dimensions`, см).
+//!
+//! Хранятся отдельно от агрегата в `a007_product_dimensions` и обновляются
+//! импортом карточек (u504). Объём единицы нужен для распределения расходов на
+//! хранение по SKU (p918, драйвер «доля объёма»).
+
+use anyhow::Result;
+use chrono::Utc;
+use sea_orm::{ConnectionTrait, Statement, Value};
+use std::collections::HashMap;
+
+use crate::shared::data::db::get_connection;
+
+/// Объём единицы в литрах по габаритам в сантиметрах; `None`, если какой-то размер не задан.
+pub fn volume_liters(
+    length_cm: Option<f64>,
+    width_cm: Option<f64>,
+    height_cm: Option<f64>,
+) -> Option<f64> {
+    let (l, w, h) = (length_cm?, width_cm?, height_cm?);
+    (l > 0.0 && w > 0.0 && h > 0.0).then(|| l * w * h / 1000.0)
+}
+
+/// Сохранить габариты товара (последние из карточки).
+pub async fn upsert(
+    marketplace_product_ref: &str,
+    length_cm: Option<f64>,
+    width_cm: Option<f64>,
+    height_cm: Option<f64>,
+) -> Result<()> {
+    let db = get_connection();
+    db.execute(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        "INSERT INTO a007_product_dimensions (
+            marketplace_product_ref, length_cm, width_cm, height_cm, volume_liters, updated_at
+        ) VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(marketplace_product_ref) DO UPDATE SET
+            length_cm = excluded.length_cm,
+            width_cm = excluded.width_cm,
+            height_cm = excluded.height_cm,
+            volume_liters = excluded.volume_liters,
+            updated_at = excluded.updated_at",
+        [
+            marketplace_product_ref.into(),
+            Value::from(length_cm),
+            Value::from(width_cm),
+            Value::from(height_cm),
+            Value::from(volume_liters(length_cm, width_cm, height_cm)),
+            Utc::now().to_rfc3339().into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Объём единицы (л) по `marketplace_sku` товаров кабинета; товары без габаритов не попадают.
+pub async fn volumes_by_sku(connection_mp_ref: &str) -> Result<HashMap<String, f64>> {
+    let db = get_connection();
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "SELECT p.marketplace_sku AS sku, d.volume_liters AS volume_liters
+             FROM a007_marketplace_product p
+             JOIN a007_product_dimensions d ON d.marketplace_product_ref = p.id
+             WHERE p.connection_mp_ref = ? AND p.is_deleted = 0
+               AND d.volume_liters IS NOT NULL AND d.volume_liters > 0",
+            [connection_mp_ref.into()],
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let sku = row.try_get::<String>("", "sku").ok()?;
+            let volume = row.try_get::<f64>("", "volume_liters").ok()?;
+            Some((sku, volume))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn volume_needs_all_three_dimensions() {
+        assert_eq!(volume_liters(Some(10.0), Some(20.0), Some(5.0)), Some(1.0));
+        assert_eq!(volume_liters(Some(10.0), None, Some(5.0)), None);
+        assert_eq!(volume_liters(Some(10.0), Some(0.0), Some(5.0)), None);
+    }
+}
@@ -1,2 +1,9 @@
+pub mod abc_xyz;
+pub mod cross_mapping;
+pub mod demand_forecast;
+pub mod dimensions;
+pub mod price_history;
 pub mod repository;
+pub mod sales_anomalies;
 pub mod service;
+pub mod stock_alerts;
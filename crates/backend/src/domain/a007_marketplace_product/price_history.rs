@@ -0,0 +1,79 @@
+//! История цен товара WB по дням: цена до скидок, цена со скидкой продавца,
+//! СПП и цена покупателя. Источник — заказы a015 (без отменённых) и продажи a012
+//! (`event_type = 'sale'`), привязанные к товару a007; за день берётся среднее
+//! по всем документам. Разница между ценой со скидкой и ценой покупателя —
+//! софинансирование WB.
+
+use anyhow::Result;
+use contracts::domain::a007_marketplace_product::aggregate::PriceHistoryPointDto;
+use sea_orm::{ConnectionTrait, Statement};
+
+use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+
+pub async fn load(
+    marketplace_product_ref: &str,
+    date_from: &str,
+    date_to: &str,
+) -> Result<Vec<PriceHistoryPointDto>> {
+    let db = get_connection();
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &format!(
+                "SELECT day, \
+                    AVG(total_price) AS total_price, \
+                    AVG(price_with_disc) AS price_with_disc, \
+                    AVG(spp) AS spp, \
+                    AVG(finished_price) AS finished_price, \
+                    COUNT(*) AS documents \
+             FROM ( \
+                 SELECT substr(document_date, 1, 10) AS day, \
+                        json_extract(line_json, '$.total_price') AS total_price, \
+                        json_extract(line_json, '$.price_with_disc') AS price_with_disc, \
+                        COALESCE(spp, json_extract(line_json, '$.spp')) AS spp, \
+                        json_extract(line_json, '$.finished_price') AS finished_price \
+                 FROM a015_wb_orders \
+                 WHERE is_deleted = 0 AND COALESCE(is_cancel, 0) = 0 \
+                   AND marketplace_product_ref = ? \
+                   AND substr(document_date, 1, 10) >= ? AND substr(document_date, 1, 10) <= ? \
+                 UNION ALL \
+                 SELECT substr(sale_date, 1, 10) AS day, \
+                        total_price, \
+                        json_extract(line_json, '$.price_effective') AS price_with_disc, \
+                        json_extract(line_json, '$.spp') AS spp, \
+                        finished_price \
+                 FROM {a012} \
+                 WHERE is_deleted = 0 AND event_type = 'sale' \
+                   AND marketplace_product_ref = ? \
+                   AND substr(sale_date, 1, 10) >= ? AND substr(sale_date, 1, 10) <= ? \
+             ) \
+             GROUP BY day \
+             ORDER BY day",
+                a012 = projection_archive::source("a012_wb_sales", Some(date_from)),
+            ),
+            [
+                marketplace_product_ref.into(),
+                date_from.into(),
+                date_to.into(),
+                marketplace_product_ref.into(),
+                date_from.into(),
+                date_to.into(),
+            ],
+        ))
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(PriceHistoryPointDto {
+                date: row.try_get("", "day").ok()?,
+                total_price: row.try_get("", "total_price").ok().flatten(),
+                price_with_disc: row.try_get("", "price_with_disc").ok().flatten(),
+                spp: row.try_get("", "spp").ok().flatten(),
+                finished_price: row.try_get("", "finished_price").ok().flatten(),
+                documents: row.try_get("", "documents").unwrap_or_default(),
+            })
+        })
+        .collect())
+}
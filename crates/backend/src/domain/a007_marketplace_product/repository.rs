@@ -1,6 +1,6 @@
 use chrono::Utc;
 use contracts::domain::a007_marketplace_product::aggregate::{
-    MarketplaceProduct, MarketplaceProductId,
+    AbcClass, MarketplaceProduct, MarketplaceProductId, XyzClass,
 };
 use contracts::domain::common::{BaseAggregate, EntityMetadata};
 use serde::{Deserialize, Serialize};
@@ -257,6 +257,8 @@ pub struct MarketplaceProductListQuery {
     pub marketplace_ref: Option<String>,
     pub connection_mp_ref: Option<String>,
     pub problems_only: bool,
+    pub abc_class: Option<AbcClass>,
+    pub xyz_class: Option<XyzClass>,
     pub search: Option<String>,
     pub sort_by: String,
     pub sort_desc: bool,
@@ -349,6 +351,15 @@ pub async fn list_paginated(
         );
     }
 
+    if let Some((sql, values)) = super::abc_xyz::class_filter_sql(
+        "a007_marketplace_product",
+        query.abc_class,
+        query.xyz_class,
+    ) {
+        use sea_orm::sea_query::Expr;
+        select = select.filter(Expr::cust_with_values(sql, values));
+    }
+
     if let Some(search) = query.search {
         if !search.is_empty() {
             let s = format!("%{}%", search);
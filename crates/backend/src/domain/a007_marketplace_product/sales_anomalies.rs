@@ -0,0 +1,517 @@
+//! Аномалии дневных продаж: сезонный z-score по дню недели.
+//!
+//! Ожидание на день — среднее продаж в штуках в тот же день недели за предыдущие
+//! N недель (по умолчанию 8), разброс — их стандартное отклонение. День помечается,
+//! если отклонение от ожидания больше порога в сигмах (по умолчанию 3) и не меньше
+//! заданного числа штук — иначе малопродаваемые товары шумят. Ряды строятся по
+//! регистру p900 (включая архив): по каждому товару a007 и итогом по кабинету.
+//!
+//! Результаты (task028) копятся в `a007_sales_anomalies`: повторный расчёт того же
+//! дня обновляет цифры, неразобранная аномалия, переставшая выходить за порог после
+//! догрузки данных, удаляется. Разобранные (с пояснением пользователя) не трогаются.
+
+use anyhow::Result;
+use chrono::{Duration, NaiveDate};
+use contracts::domain::a007_marketplace_product::aggregate::{
+    SalesAnomalyDirection, SalesAnomalyDto, SalesAnomalyListResponse,
+};
+use sea_orm::{ConnectionTrait, Statement, TransactionTrait, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+
+/// Пороги детектора (настраиваются в конфиге task028).
+#[derive(Debug, Clone, Copy)]
+pub struct AnomalyThresholds {
+    /// Сколько предыдущих недель берётся для ожидания.
+    pub history_weeks: i64,
+    /// Минимум точек истории: у новинок ожидание не строится.
+    pub min_history: usize,
+    /// Порог отклонения в сигмах.
+    pub z_threshold: f64,
+    /// Минимальное отклонение от ожидания, шт.
+    pub min_qty_delta: f64,
+}
+
+impl Default for AnomalyThresholds {
+    fn default() -> Self {
+        Self {
+            history_weeks: 8,
+            min_history: 4,
+            z_threshold: 3.0,
+            min_qty_delta: 5.0,
+        }
+    }
+}
+
+/// Ожидание на день и отклонение от него.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Deviation {
+    pub expected: f64,
+    pub std_dev: f64,
+    pub z_score: f64,
+    pub direction: SalesAnomalyDirection,
+}
+
+/// Сравнить продажи дня с историей того же дня недели. Сигма снизу ограничена
+/// корнем из среднего (пуассоновский шум) и единицей, чтобы ровная история не
+/// превращала любое отклонение в бесконечный z.
+pub fn evaluate(history: &[f64], actual: f64, thresholds: &AnomalyThresholds) -> Option<Deviation> {
+    if history.len() < thresholds.min_history.max(1) {
+        return None;
+    }
+    let count = history.len() as f64;
+    let expected = history.iter().sum::<f64>() / count;
+    let std_dev = (history
+        .iter()
+        .map(|value| (value - expected).powi(2))
+        .sum::<f64>()
+        / count)
+        .sqrt();
+    let sigma = std_dev.max(expected.abs().sqrt()).max(1.0);
+    let delta = actual - expected;
+    let z_score = delta / sigma;
+    if z_score.abs() < thresholds.z_threshold || delta.abs() < thresholds.min_qty_delta {
+        return None;
+    }
+    Some(Deviation {
+        expected,
+        std_dev,
+        z_score,
+        direction: if delta > 0.0 {
+            SalesAnomalyDirection::Spike
+        } else {
+            SalesAnomalyDirection::Drop
+        },
+    })
+}
+
+/// Найденная аномалия дня.
+#[derive(Debug, Clone)]
+pub struct SalesAnomalyRow {
+    pub connection_mp_ref: String,
+    /// Пусто — итог кабинета.
+    pub marketplace_product_ref: String,
+    pub article: String,
+    pub sale_date: String,
+    pub deviation: Deviation,
+    pub actual_qty: f64,
+    pub actual_revenue: f64,
+    pub expected_revenue: f64,
+}
+
+impl SalesAnomalyRow {
+    fn key(&self) -> (String, String, String) {
+        (
+            self.connection_mp_ref.clone(),
+            self.marketplace_product_ref.clone(),
+            self.sale_date.clone(),
+        )
+    }
+}
+
+#[derive(Default)]
+struct Series {
+    article: String,
+    /// День → (шт., выручка).
+    days: BTreeMap<NaiveDate, (f64, f64)>,
+}
+
+impl Series {
+    fn add(&mut self, date: NaiveDate, qty: f64, revenue: f64) {
+        let day = self.days.entry(date).or_default();
+        day.0 += qty;
+        day.1 += revenue;
+    }
+}
+
+/// Проверить дни `check_from..=check_to` по всем товарам и кабинетам.
+pub async fn detect(
+    check_from: NaiveDate,
+    check_to: NaiveDate,
+    thresholds: &AnomalyThresholds,
+) -> Result<Vec<SalesAnomalyRow>> {
+    let db = get_connection();
+    let history_from = check_from - Duration::weeks(thresholds.history_weeks.max(1));
+    let date_from = history_from.format("%Y-%m-%d").to_string();
+    let date_to = check_to.format("%Y-%m-%d").to_string();
+    let sql = format!(
+        "SELECT p900.connection_mp_ref, \
+                COALESCE(p900.marketplace_product_ref, '') AS product_ref, \
+                MAX(COALESCE(p.article, '')) AS article, \
+                substr(p900.sale_date, 1, 10) AS day, \
+                SUM(p900.qty) AS qty, \
+                SUM(COALESCE(p900.amount_line, 0)) AS revenue \
+         FROM {} p900 \
+         LEFT JOIN a007_marketplace_product p ON p.id = p900.marketplace_product_ref \
+         WHERE p900.sale_date >= ? \
+           AND substr(p900.sale_date, 1, 10) <= ? \
+         GROUP BY p900.connection_mp_ref, product_ref, day",
+        projection_archive::source("p900_sales_register", Some(&date_from))
+    );
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            [date_from.into(), date_to.into()],
+        ))
+        .await?;
+
+    // (кабинет, товар) → ряд; товар "" — итог кабинета.
+    let mut series: HashMap<(String, String), Series> = HashMap::new();
+    for row in rows {
+        let connection_mp_ref: String = row.try_get("", "connection_mp_ref").unwrap_or_default();
+        let product_ref: String = row.try_get("", "product_ref").unwrap_or_default();
+        let day: String = row.try_get("", "day").unwrap_or_default();
+        let Ok(date) = NaiveDate::parse_from_str(&day, "%Y-%m-%d") else {
+            continue;
+        };
+        let qty: f64 = row.try_get("", "qty").unwrap_or_default();
+        let revenue: f64 = row.try_get("", "revenue").unwrap_or_default();
+
+        series
+            .entry((connection_mp_ref.clone(), String::new()))
+            .or_default()
+            .add(date, qty, revenue);
+        if !product_ref.is_empty() {
+            let product = series.entry((connection_mp_ref, product_ref)).or_default();
+            product.article = row.try_get("", "article").unwrap_or_default();
+            product.add(date, qty, revenue);
+        }
+    }
+
+    let mut anomalies = Vec::new();
+    for ((connection_mp_ref, product_ref), series) in series {
+        // До первой продажи в окне товара ещё не было — нули там не история.
+        let Some(first_day) = series.days.keys().next().copied() else {
+            continue;
+        };
+        let mut date = check_from;
+        while date <= check_to {
+            let history: Vec<(f64, f64)> = (1..=thresholds.history_weeks)
+                .map(|weeks| date - Duration::weeks(weeks))
+                .filter(|day| *day >= first_day)
+                .map(|day| series.days.get(&day).copied().unwrap_or_default())
+                .collect();
+            let (actual_qty, actual_revenue) = series.days.get(&date).copied().unwrap_or_default();
+            let history_qty: Vec<f64> = history.iter().map(|(qty, _)| *qty).collect();
+            if let Some(deviation) = evaluate(&history_qty, actual_qty, thresholds) {
+                anomalies.push(SalesAnomalyRow {
+                    connection_mp_ref: connection_mp_ref.clone(),
+                    marketplace_product_ref: product_ref.clone(),
+                    article: series.article.clone(),
+                    sale_date: date.format("%Y-%m-%d").to_string(),
+                    deviation,
+                    actual_qty,
+                    actual_revenue,
+                    expected_revenue: history.iter().map(|(_, revenue)| revenue).sum::<f64>()
+                        / history.len() as f64,
+                });
+            }
+            date += Duration::days(1);
+        }
+    }
+    anomalies.sort_by(|a, b| {
+        a.sale_date.cmp(&b.sale_date).then(
+            b.deviation
+                .z_score
+                .abs()
+                .total_cmp(&a.deviation.z_score.abs()),
+        )
+    });
+    Ok(anomalies)
+}
+
+/// Сохранить расчёт дней `check_from..=check_to`. Возвращает аномалии, которых в
+/// таблице ещё не было, — только о них отправляются уведомления.
+pub async fn store(
+    anomalies: &[SalesAnomalyRow],
+    check_from: &str,
+    check_to: &str,
+    detected_at: &str,
+) -> Result<Vec<SalesAnomalyRow>> {
+    let db = get_connection();
+    let existing: HashMap<(String, String, String), bool> = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "SELECT connection_mp_ref, marketplace_product_ref, sale_date, explanation \
+             FROM a007_sales_anomalies WHERE sale_date >= ? AND sale_date <= ?",
+            [check_from.into(), check_to.into()],
+        ))
+        .await?
+        .into_iter()
+        .filter_map(|row| {
+            let key = (
+                row.try_get::<String>("", "connection_mp_ref").ok()?,
+                row.try_get::<String>("", "marketplace_product_ref").ok()?,
+                row.try_get::<String>("", "sale_date").ok()?,
+            );
+            let explained = row
+                .try_get::<Option<String>>("", "explanation")
+                .ok()
+                .flatten()
+                .is_some();
+            Some((key, explained))
+        })
+        .collect();
+    let found: HashSet<(String, String, String)> =
+        anomalies.iter().map(SalesAnomalyRow::key).collect();
+
+    let txn = db.begin().await?;
+    for ((connection_mp_ref, product_ref, sale_date), explained) in &existing {
+        let key = (
+            connection_mp_ref.clone(),
+            product_ref.clone(),
+            sale_date.clone(),
+        );
+        if *explained || found.contains(&key) {
+            continue;
+        }
+        txn.execute(Statement::from_sql_and_values(
+            txn.get_database_backend(),
+            "DELETE FROM a007_sales_anomalies \
+             WHERE connection_mp_ref = ? AND marketplace_product_ref = ? AND sale_date = ?",
+            [
+                connection_mp_ref.clone().into(),
+                product_ref.clone().into(),
+                sale_date.clone().into(),
+            ],
+        ))
+        .await?;
+    }
+    for anomaly in anomalies {
+        let deviation = &anomaly.deviation;
+        txn.execute(Statement::from_sql_and_values(
+            txn.get_database_backend(),
+            "INSERT INTO a007_sales_anomalies (
+                id, connection_mp_ref, marketplace_product_ref, sale_date, direction,
+                actual_qty, expected_qty, std_dev, z_score, actual_revenue, expected_revenue,
+                detected_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(connection_mp_ref, marketplace_product_ref, sale_date) DO UPDATE SET
+                direction = excluded.direction,
+                actual_qty = excluded.actual_qty,
+                expected_qty = excluded.expected_qty,
+                std_dev = excluded.std_dev,
+                z_score = excluded.z_score,
+                actual_revenue = excluded.actual_revenue,
+                expected_revenue = excluded.expected_revenue,
+                updated_at = excluded.updated_at",
+            [
+                uuid::Uuid::new_v4().to_string().into(),
+                anomaly.connection_mp_ref.clone().into(),
+                anomaly.marketplace_product_ref.clone().into(),
+                anomaly.sale_date.clone().into(),
+                deviation.direction.code().into(),
+                anomaly.actual_qty.into(),
+                deviation.expected.into(),
+                deviation.std_dev.into(),
+                deviation.z_score.into(),
+                anomaly.actual_revenue.into(),
+                anomaly.expected_revenue.into(),
+                detected_at.into(),
+                detected_at.into(),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+
+    Ok(anomalies
+        .iter()
+        .filter(|anomaly| !existing.contains_key(&anomaly.key()))
+        .cloned()
+        .collect())
+}
+
+/// Фильтры списка на разбор.
+#[derive(Debug, Clone, Default)]
+pub struct SalesAnomalyListQuery {
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub connection_mp_ref: Option<String>,
+    pub direction: Option<SalesAnomalyDirection>,
+    /// Some(false) — только неразобранные, Some(true) — только объяснённые.
+    pub explained: Option<bool>,
+    /// Только итоги кабинетов (без разреза по товарам).
+    pub totals_only: bool,
+}
+
+/// Список аномалий: свежие дни и сильные отклонения сверху.
+pub async fn list(query: &SalesAnomalyListQuery) -> Result<SalesAnomalyListResponse> {
+    let db = get_connection();
+    let mut conditions = vec!["1 = 1".to_string()];
+    let mut values: Vec<Value> = Vec::new();
+    if let Some(date_from) = query.date_from.as_deref().filter(|v| !v.is_empty()) {
+        conditions.push("a.sale_date >= ?".to_string());
+        values.push(date_from.into());
+    }
+    if let Some(date_to) = query.date_to.as_deref().filter(|v| !v.is_empty()) {
+        conditions.push("a.sale_date <= ?".to_string());
+        values.push(date_to.into());
+    }
+    if let Some(connection_mp_ref) = query.connection_mp_ref.as_deref().filter(|v| !v.is_empty()) {
+        conditions.push("a.connection_mp_ref = ?".to_string());
+        values.push(connection_mp_ref.into());
+    }
+    if let Some(direction) = query.direction {
+        conditions.push("a.direction = ?".to_string());
+        values.push(direction.code().into());
+    }
+    match query.explained {
+        Some(true) => conditions.push("a.explanation IS NOT NULL".to_string()),
+        Some(false) => conditions.push("a.explanation IS NULL".to_string()),
+        None => {}
+    }
+    if query.totals_only {
+        conditions.push("a.marketplace_product_ref = ''".to_string());
+    }
+
+    let sql = format!(
+        "SELECT a.id, a.connection_mp_ref, a.marketplace_product_ref, a.sale_date, a.direction, \
+                a.actual_qty, a.expected_qty, a.std_dev, a.z_score, \
+                a.actual_revenue, a.expected_revenue, a.detected_at, \
+                a.explanation, a.explained_by, a.explained_at, \
+                p.article, p.description AS product_name \
+         FROM a007_sales_anomalies a \
+         LEFT JOIN a007_marketplace_product p ON p.id = a.marketplace_product_ref \
+         WHERE {} \
+         ORDER BY a.sale_date DESC, ABS(a.z_score) DESC \
+         LIMIT 2000",
+        conditions.join(" AND ")
+    );
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+    let items = rows
+        .into_iter()
+        .filter_map(|row| {
+            let direction =
+                SalesAnomalyDirection::from_code(&row.try_get::<String>("", "direction").ok()?)?;
+            let product_ref: String = row
+                .try_get("", "marketplace_product_ref")
+                .unwrap_or_default();
+            Some(SalesAnomalyDto {
+                id: row.try_get("", "id").ok()?,
+                connection_mp_ref: row.try_get("", "connection_mp_ref").unwrap_or_default(),
+                marketplace_product_ref: Some(product_ref).filter(|v| !v.is_empty()),
+                article: row.try_get("", "article").ok().flatten(),
+                product_name: row.try_get("", "product_name").ok().flatten(),
+                sale_date: row.try_get("", "sale_date").unwrap_or_default(),
+                direction,
+                actual_qty: row.try_get("", "actual_qty").unwrap_or_default(),
+                expected_qty: row.try_get("", "expected_qty").unwrap_or_default(),
+                std_dev: row.try_get("", "std_dev").unwrap_or_default(),
+                z_score: row.try_get("", "z_score").unwrap_or_default(),
+                actual_revenue: row.try_get("", "actual_revenue").unwrap_or_default(),
+                expected_revenue: row.try_get("", "expected_revenue").unwrap_or_default(),
+                detected_at: row.try_get("", "detected_at").unwrap_or_default(),
+                explanation: row.try_get("", "explanation").ok().flatten(),
+                explained_by: row.try_get("", "explained_by").ok().flatten(),
+                explained_at: row.try_get("", "explained_at").ok().flatten(),
+            })
+        })
+        .collect();
+
+    let open_count = db
+        .query_one(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT COUNT(*) AS cnt FROM a007_sales_anomalies WHERE explanation IS NULL",
+        ))
+        .await?
+        .and_then(|row| row.try_get::<i64>("", "cnt").ok())
+        .unwrap_or_default();
+
+    Ok(SalesAnomalyListResponse { items, open_count })
+}
+
+/// Пометить аномалию объяснённой. false — аномалии с таким id нет.
+pub async fn explain(id: &str, explanation: &str, explained_by: &str) -> Result<bool> {
+    let db = get_connection();
+    let result = db
+        .execute(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "UPDATE a007_sales_anomalies \
+             SET explanation = ?, explained_by = ?, explained_at = ? \
+             WHERE id = ?",
+            [
+                explanation.into(),
+                explained_by.into(),
+                chrono::Utc::now().to_rfc3339().into(),
+                id.into(),
+            ],
+        ))
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Текст уведомления о новых аномалиях (первые `limit` строк).
+pub fn notification_text(anomalies: &[SalesAnomalyRow], limit: usize) -> String {
+    let mut lines: Vec<String> = anomalies
+        .iter()
+        .take(limit)
+        .map(|anomaly| {
+            let subject = if anomaly.marketplace_product_ref.is_empty() {
+                "итог кабинета".to_string()
+            } else if anomaly.article.is_empty() {
+                anomaly.marketplace_product_ref.clone()
+            } else {
+                anomaly.article.clone()
+            };
+            format!(
+                "- {} {}: {} — {:.0} шт. при ожидании {:.1} (z = {:.1})",
+                anomaly.sale_date,
+                subject,
+                anomaly.deviation.direction.label(),
+                anomaly.actual_qty,
+                anomaly.deviation.expected,
+                anomaly.deviation.z_score
+            )
+        })
+        .collect();
+    if anomalies.len() > limit {
+        lines.push(format!("… и ещё {}", anomalies.len() - limit));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_expectation_without_enough_history() {
+        let thresholds = AnomalyThresholds::default();
+        assert!(evaluate(&[10.0, 10.0, 10.0], 100.0, &thresholds).is_none());
+    }
+
+    #[test]
+    fn spike_and_drop_against_same_weekday_history() {
+        let thresholds = AnomalyThresholds::default();
+        let history = [20.0, 22.0, 18.0, 21.0, 19.0, 20.0];
+
+        let spike = evaluate(&history, 45.0, &thresholds).expect("spike expected");
+        assert_eq!(spike.direction, SalesAnomalyDirection::Spike);
+        assert!((spike.expected - 20.0).abs() < 1e-9);
+        assert!(spike.z_score > 3.0);
+
+        let drop = evaluate(&history, 0.0, &thresholds).expect("drop expected");
+        assert_eq!(drop.direction, SalesAnomalyDirection::Drop);
+
+        assert!(evaluate(&history, 24.0, &thresholds).is_none());
+    }
+
+    #[test]
+    fn flat_low_volume_history_is_not_flagged_for_small_deltas() {
+        // Сигма ограничена снизу: ровная история 1 шт./день и 4 шт. — не аномалия
+        // по абсолютному порогу, хотя z большой.
+        let thresholds = AnomalyThresholds::default();
+        assert!(evaluate(&[1.0; 8], 4.0, &thresholds).is_none());
+        assert!(evaluate(&[1.0; 8], 7.0, &thresholds).is_some());
+    }
+}
@@ -0,0 +1,354 @@
+//! Складские алерты по товарам WB: «запаса меньше чем на N дней» и «нет продаж
+//! M дней при ненулевом остатке».
+//!
+//! Остаток берётся из последнего снимка a037 по кабинету, скорость продаж — из
+//! a012 (только `event_type = 'sale'`) за скользящее окно. Результат расчёта
+//! (task026) целиком заменяет таблицу `a007_stock_alerts`; список a007 читает её
+//! для бейджей по ключу `connection_mp_ref + marketplace_sku` (для WB sku = nm_id).
+
+use anyhow::Result;
+use chrono::{Duration, NaiveDate};
+use contracts::domain::a007_marketplace_product::aggregate::{StockAlertBadgeDto, StockAlertKind};
+use sea_orm::{ConnectionTrait, Statement, TransactionTrait, Value};
+use std::collections::{HashMap, HashSet};
+
+use crate::shared::data::db::get_connection;
+
+/// Пороги алертов (настраиваются в конфиге task026).
+#[derive(Debug, Clone, Copy)]
+pub struct StockAlertThresholds {
+    /// Алерт, если остатка хватит меньше чем на столько дней.
+    pub low_cover_days: i64,
+    /// Алерт, если последняя продажа была не позже чем столько дней назад.
+    pub stale_days: i64,
+    /// Окно расчёта средней скорости продаж, дней.
+    pub velocity_window_days: i64,
+}
+
+/// Сработавший алерт по товару.
+#[derive(Debug, Clone)]
+pub struct StockAlertRow {
+    pub connection_mp_ref: String,
+    pub marketplace_sku: String,
+    pub kind: StockAlertKind,
+    pub vendor_code: String,
+    pub title: String,
+    pub stock: i64,
+    pub days_of_cover: Option<f64>,
+    pub last_sale_date: Option<String>,
+}
+
+impl StockAlertRow {
+    fn key(&self) -> (String, String, StockAlertKind) {
+        (
+            self.connection_mp_ref.clone(),
+            self.marketplace_sku.clone(),
+            self.kind,
+        )
+    }
+}
+
+/// Решение по одному товару. Без остатка алертов нет; «залежалость» проверяется
+/// первой — у такого товара скорость за окно нулевая и запас бесконечен.
+pub fn evaluate(
+    stock: i64,
+    window_qty: f64,
+    last_sale_date: Option<NaiveDate>,
+    today: NaiveDate,
+    thresholds: &StockAlertThresholds,
+) -> Option<(StockAlertKind, Option<f64>)> {
+    if stock <= 0 {
+        return None;
+    }
+
+    let is_stale = match last_sale_date {
+        Some(date) => (today - date).num_days() >= thresholds.stale_days,
+        None => true,
+    };
+    if is_stale {
+        return Some((StockAlertKind::StaleStock, None));
+    }
+
+    if thresholds.velocity_window_days <= 0 || window_qty <= 0.0 {
+        return None;
+    }
+    let daily_velocity = window_qty / thresholds.velocity_window_days as f64;
+    let days_of_cover = stock as f64 / daily_velocity;
+    if days_of_cover < thresholds.low_cover_days as f64 {
+        Some((StockAlertKind::LowCover, Some(days_of_cover)))
+    } else {
+        None
+    }
+}
+
+/// Рассчитать алерты по всем кабинетам на `today`.
+pub async fn compute(
+    thresholds: &StockAlertThresholds,
+    today: NaiveDate,
+) -> Result<Vec<StockAlertRow>> {
+    let window_from = (today - Duration::days(thresholds.velocity_window_days))
+        .format("%Y-%m-%d")
+        .to_string();
+    let velocity: HashMap<(String, i64), (f64, Option<String>)> =
+        crate::domain::a012_wb_sales::repository::sales_velocity_by_nm_id(&window_from)
+            .await?
+            .into_iter()
+            .map(|row| {
+                (
+                    (row.connection_id, row.nm_id),
+                    (row.window_qty, row.last_sale_date),
+                )
+            })
+            .collect();
+
+    let stocks =
+        crate::domain::a037_wb_product_snapshot::repository::stock_rows(None, None, usize::MAX, 0)
+            .await?;
+
+    let mut alerts = Vec::new();
+    for row in stocks.rows {
+        let stock = row.stock_wb + row.stock_mp;
+        let (window_qty, last_sale_date) = velocity
+            .get(&(row.connection_id.clone(), row.nm_id))
+            .cloned()
+            .unwrap_or((0.0, None));
+        let last_sale = last_sale_date
+            .as_deref()
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+
+        if let Some((kind, days_of_cover)) =
+            evaluate(stock, window_qty, last_sale, today, thresholds)
+        {
+            alerts.push(StockAlertRow {
+                connection_mp_ref: row.connection_id,
+                marketplace_sku: row.nm_id.to_string(),
+                kind,
+                vendor_code: row.vendor_code,
+                title: row.title,
+                stock,
+                days_of_cover,
+                last_sale_date,
+            });
+        }
+    }
+
+    Ok(alerts)
+}
+
+/// Заменить сохранённые алерты новым расчётом. Возвращает алерты, которых не было
+/// в предыдущем расчёте, — только о них отправляются уведомления.
+pub async fn replace_all(
+    alerts: &[StockAlertRow],
+    computed_at: &str,
+) -> Result<Vec<StockAlertRow>> {
+    let db = get_connection();
+
+    let previous: HashSet<(String, String, StockAlertKind)> = db
+        .query_all(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT connection_mp_ref, marketplace_sku, kind FROM a007_stock_alerts",
+        ))
+        .await?
+        .into_iter()
+        .filter_map(|row| {
+            let connection_mp_ref: String = row.try_get("", "connection_mp_ref").ok()?;
+            let marketplace_sku: String = row.try_get("", "marketplace_sku").ok()?;
+            let kind: String = row.try_get("", "kind").ok()?;
+            Some((
+                connection_mp_ref,
+                marketplace_sku,
+                StockAlertKind::from_code(&kind)?,
+            ))
+        })
+        .collect();
+
+    let txn = db.begin().await?;
+    txn.execute(Statement::from_string(
+        txn.get_database_backend(),
+        "DELETE FROM a007_stock_alerts",
+    ))
+    .await?;
+    for alert in alerts {
+        txn.execute(Statement::from_sql_and_values(
+            txn.get_database_backend(),
+            "INSERT INTO a007_stock_alerts (
+                connection_mp_ref, marketplace_sku, kind, vendor_code, title,
+                stock, days_of_cover, last_sale_date, computed_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            [
+                alert.connection_mp_ref.clone().into(),
+                alert.marketplace_sku.clone().into(),
+                alert.kind.code().into(),
+                alert.vendor_code.clone().into(),
+                alert.title.clone().into(),
+                alert.stock.into(),
+                Value::from(alert.days_of_cover),
+                Value::from(alert.last_sale_date.clone()),
+                computed_at.into(),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+
+    Ok(alerts
+        .iter()
+        .filter(|alert| !previous.contains(&alert.key()))
+        .cloned()
+        .collect())
+}
+
+/// Алерты для страницы списка: ключ — `(connection_mp_ref, marketplace_sku)`.
+pub async fn badges_for(
+    keys: &[(String, String)],
+) -> Result<HashMap<(String, String), StockAlertBadgeDto>> {
+    if keys.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let db = get_connection();
+    let placeholders = vec!["?"; keys.len()].join(", ");
+    let sql = format!(
+        "SELECT connection_mp_ref, marketplace_sku, kind, stock, days_of_cover, last_sale_date \
+         FROM a007_stock_alerts WHERE marketplace_sku IN ({placeholders})"
+    );
+    let values: Vec<Value> = keys.iter().map(|(_, sku)| sku.clone().into()).collect();
+    let wanted: HashSet<&(String, String)> = keys.iter().collect();
+
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let key = (
+                row.try_get::<String>("", "connection_mp_ref").ok()?,
+                row.try_get::<String>("", "marketplace_sku").ok()?,
+            );
+            if !wanted.contains(&key) {
+                return None;
+            }
+            let kind = StockAlertKind::from_code(&row.try_get::<String>("", "kind").ok()?)?;
+            Some((
+                key,
+                StockAlertBadgeDto {
+                    kind,
+                    stock: row.try_get("", "stock").unwrap_or_default(),
+                    days_of_cover: row.try_get("", "days_of_cover").ok().flatten(),
+                    last_sale_date: row.try_get("", "last_sale_date").ok().flatten(),
+                },
+            ))
+        })
+        .collect())
+}
+
+/// Текст письма о новых алертах.
+pub fn notification_text(alerts: &[StockAlertRow], thresholds: &StockAlertThresholds) -> String {
+    let mut low_cover = Vec::new();
+    let mut stale = Vec::new();
+    for alert in alerts {
+        match alert.kind {
+            StockAlertKind::LowCover => low_cover.push(format!(
+                "- {} ({}, nm {}): остаток {} шт., запас {:.1} дн.",
+                alert.vendor_code,
+                alert.title,
+                alert.marketplace_sku,
+                alert.stock,
+                alert.days_of_cover.unwrap_or_default()
+            )),
+            StockAlertKind::StaleStock => stale.push(format!(
+                "- {} ({}, nm {}): остаток {} шт., последняя продажа {}",
+                alert.vendor_code,
+                alert.title,
+                alert.marketplace_sku,
+                alert.stock,
+                alert.last_sale_date.as_deref().unwrap_or("нет")
+            )),
+        }
+    }
+
+    let mut body = String::new();
+    if !low_cover.is_empty() {
+        body.push_str(&format!(
+            "Запаса меньше чем на {} дн.:\n{}\n\n",
+            thresholds.low_cover_days,
+            low_cover.join("\n")
+        ));
+    }
+    if !stale.is_empty() {
+        body.push_str(&format!(
+            "Нет продаж {}+ дн. при ненулевом остатке:\n{}\n",
+            thresholds.stale_days,
+            stale.join("\n")
+        ));
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thresholds() -> StockAlertThresholds {
+        StockAlertThresholds {
+            low_cover_days: 14,
+            stale_days: 60,
+            velocity_window_days: 28,
+        }
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn no_alert_without_stock() {
+        assert!(evaluate(0, 100.0, None, date("2026-10-01"), &thresholds()).is_none());
+    }
+
+    #[test]
+    fn low_cover_when_stock_runs_out_before_threshold() {
+        // 56 шт. за 28 дней = 2 шт./день → 20 шт. хватит на 10 дней.
+        let result = evaluate(
+            20,
+            56.0,
+            Some(date("2026-09-30")),
+            date("2026-10-01"),
+            &thresholds(),
+        );
+        let (kind, cover) = result.expect("alert expected");
+        assert_eq!(kind, StockAlertKind::LowCover);
+        assert!((cover.unwrap() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn enough_cover_is_not_alerted() {
+        assert!(evaluate(
+            100,
+            56.0,
+            Some(date("2026-09-30")),
+            date("2026-10-01"),
+            &thresholds()
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn stale_when_last_sale_is_old_or_missing() {
+        let today = date("2026-10-01");
+        assert_eq!(
+            evaluate(5, 0.0, Some(date("2026-08-01")), today, &thresholds()).map(|r| r.0),
+            Some(StockAlertKind::StaleStock)
+        );
+        assert_eq!(
+            evaluate(5, 0.0, None, today, &thresholds()).map(|r| r.0),
+            Some(StockAlertKind::StaleStock)
+        );
+        assert!(evaluate(5, 0.0, Some(date("2026-09-01")), today, &thresholds()).is_none());
+    }
+}
@@ -1,7 +1,11 @@
 use super::repository;
 use anyhow::Result;
+use contracts::system::document_audit::DocumentAuditAction;
 use uuid::Uuid;
 
+use crate::projections::{p900_mp_sales_register, p904_sales_data};
+use crate::shared::data::unit_of_work::UnitOfWork;
+
 /// Провести документ (установить is_posted = true и создать проекцию с отрицательными значениями)
 pub async fn post_document(id: Uuid) -> Result<()> {
     // Загрузить документ
@@ -13,18 +17,35 @@ pub async fn post_document(id: Uuid) -> Result<()> {
     document.base.metadata.is_posted = true;
     document.before_write();
 
-    // Сохранить документ
-    repository::update(&document).await?;
-
-    // Удалить старые проекции (если были)
-    crate::projections::p900_mp_sales_register::service::delete_by_registrator(&id.to_string())
-        .await?;
-    crate::projections::p904_sales_data::repository::delete_by_registrator(&id.to_string()).await?;
+    // Строки проекций собираем до транзакции (возврат = отрицательные значения)
+    let registrator_ref = id.to_string();
+    let p900_entry =
+        p900_mp_sales_register::projection_builder::from_ozon_returns(&document, &registrator_ref)
+            .await?;
+    let p904_entries =
+        p904_sales_data::projection_builder::from_ozon_returns(&document, &registrator_ref).await?;
 
-    // Создать новые проекции (возврат = отрицательные значения)
-    crate::projections::p900_mp_sales_register::service::project_ozon_returns(&document, id)
+    // Документ и замена проекций — одна транзакция
+    let uow = UnitOfWork::begin().await?;
+    repository::update_with_conn(uow.conn(), &document).await?;
+    p900_mp_sales_register::repository::delete_by_registrator_with_conn(
+        uow.conn(),
+        &registrator_ref,
+    )
+    .await?;
+    p904_sales_data::repository::delete_by_registrator_with_conn(uow.conn(), &registrator_ref)
         .await?;
-    crate::projections::p904_sales_data::service::project_ozon_returns(&document, id).await?;
+    p900_mp_sales_register::repository::upsert_entry_with_conn(uow.conn(), &p900_entry).await?;
+    for entry in &p904_entries {
+        p904_sales_data::repository::upsert_entry_with_conn(uow.conn(), entry).await?;
+    }
+    uow.record_audit(
+        "a009_ozon_returns",
+        &id.to_string(),
+        DocumentAuditAction::Posted,
+    )
+    .await?;
+    uow.commit().await?;
 
     tracing::info!(
         "Posted document a009 (OZON Return): {} - projections created (P900 + P904) with negative qty: -{}",
@@ -46,14 +67,32 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
     document.base.metadata.is_posted = false;
     document.before_write();
 
-    // Сохранить документ
-    repository::update(&document).await?;
-
-    // Удалить проекции
-    crate::projections::p900_mp_sales_register::service::delete_by_registrator(&id.to_string())
+    let registrator_ref = id.to_string();
+    let uow = UnitOfWork::begin().await?;
+    repository::update_with_conn(uow.conn(), &document).await?;
+    p900_mp_sales_register::repository::delete_by_registrator_with_conn(
+        uow.conn(),
+        &registrator_ref,
+    )
+    .await?;
+    p904_sales_data::repository::delete_by_registrator_with_conn(uow.conn(), &registrator_ref)
         .await?;
-    crate::projections::p904_sales_data::repository::delete_by_registrator(&id.to_string()).await?;
+    uow.record_audit(
+        "a009_ozon_returns",
+        &id.to_string(),
+        DocumentAuditAction::Unposted,
+    )
+    .await?;
+    uow.commit().await?;
 
     tracing::info!("Unposted document a009 (OZON Return): {}", id);
     Ok(())
 }
+
+/// Строки p904 возврата (отрицательные) без записи в БД — для массовой пересборки p904.
+pub async fn build_p904_rows(id: Uuid) -> Result<Vec<p904_sales_data::repository::Model>> {
+    let document = repository::get_by_id(id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Document not found: {}", id))?;
+    p904_sales_data::projection_builder::from_ozon_returns(&document, &id.to_string()).await
+}
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use sea_orm::entity::prelude::*;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set};
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, Set};
 
 use crate::shared::data::db::get_connection;
 
@@ -141,6 +141,13 @@ pub async fn insert(aggregate: &OzonReturns) -> anyhow::Result<Uuid> {
 }
 
 pub async fn update(aggregate: &OzonReturns) -> anyhow::Result<()> {
+    update_with_conn(conn(), aggregate).await
+}
+
+pub async fn update_with_conn<C: ConnectionTrait>(
+    db: &C,
+    aggregate: &OzonReturns,
+) -> anyhow::Result<()> {
     let id = aggregate.base.id.value().to_string();
     let active = ActiveModel {
         id: Set(id),
@@ -169,7 +176,7 @@ pub async fn update(aggregate: &OzonReturns) -> anyhow::Result<()> {
         version: Set(aggregate.base.metadata.version),
         created_at: sea_orm::ActiveValue::NotSet,
     };
-    active.update(conn()).await?;
+    active.update(db).await?;
     Ok(())
 }
 
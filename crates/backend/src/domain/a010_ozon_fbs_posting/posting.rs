@@ -1,7 +1,11 @@
 use super::repository;
 use anyhow::Result;
+use contracts::system::document_audit::DocumentAuditAction;
 use uuid::Uuid;
 
+use crate::projections::{p900_mp_sales_register, p904_sales_data};
+use crate::shared::data::unit_of_work::UnitOfWork;
+
 /// Провести документ (установить is_posted = true и создать проекции если статус DELIVERED)
 pub async fn post_document(id: Uuid) -> Result<()> {
     // Загрузить документ
@@ -13,19 +17,44 @@ pub async fn post_document(id: Uuid) -> Result<()> {
     document.is_posted = true;
     document.before_write();
 
-    // Сохранить документ
-    repository::upsert_document(&document).await?;
+    // Строки проекций собираем до транзакции — только DELIVERED формирует движения
+    let registrator_ref = id.to_string();
+    let delivered = document.state.status_norm == "DELIVERED";
+    let (p900_entries, p904_entries) = if delivered {
+        (
+            p900_mp_sales_register::projection_builder::from_ozon_fbs(&document, &registrator_ref)
+                .await?,
+            p904_sales_data::projection_builder::from_ozon_fbs(&document, &registrator_ref).await?,
+        )
+    } else {
+        (Vec::new(), Vec::new())
+    };
 
-    // Удалить старые проекции (если были)
-    crate::projections::p900_mp_sales_register::service::delete_by_registrator(&id.to_string())
+    // Документ и замена проекций — одна транзакция
+    let uow = UnitOfWork::begin().await?;
+    repository::upsert_document_with_conn(uow.conn(), &document).await?;
+    p900_mp_sales_register::repository::delete_by_registrator_with_conn(
+        uow.conn(),
+        &registrator_ref,
+    )
+    .await?;
+    p904_sales_data::repository::delete_by_registrator_with_conn(uow.conn(), &registrator_ref)
         .await?;
-    crate::projections::p904_sales_data::repository::delete_by_registrator(&id.to_string()).await?;
+    for entry in &p900_entries {
+        p900_mp_sales_register::repository::upsert_entry_with_conn(uow.conn(), entry).await?;
+    }
+    for entry in &p904_entries {
+        p904_sales_data::repository::upsert_entry_with_conn(uow.conn(), entry).await?;
+    }
+    uow.record_audit(
+        "a010_ozon_fbs_posting",
+        &id.to_string(),
+        DocumentAuditAction::Posted,
+    )
+    .await?;
+    uow.commit().await?;
 
-    // Создать новые проекции только для DELIVERED
-    if document.state.status_norm == "DELIVERED" {
-        crate::projections::p900_mp_sales_register::service::project_ozon_fbs(&document, id)
-            .await?;
-        crate::projections::p904_sales_data::service::project_ozon_fbs(&document, id).await?;
+    if delivered {
         tracing::info!(
             "Posted document a010: {} with status DELIVERED - projections created (P900 + P904)",
             id
@@ -52,14 +81,36 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
     document.is_posted = false;
     document.before_write();
 
-    // Сохранить документ
-    repository::upsert_document(&document).await?;
-
-    // Удалить проекции
-    crate::projections::p900_mp_sales_register::service::delete_by_registrator(&id.to_string())
+    let registrator_ref = id.to_string();
+    let uow = UnitOfWork::begin().await?;
+    repository::upsert_document_with_conn(uow.conn(), &document).await?;
+    p900_mp_sales_register::repository::delete_by_registrator_with_conn(
+        uow.conn(),
+        &registrator_ref,
+    )
+    .await?;
+    p904_sales_data::repository::delete_by_registrator_with_conn(uow.conn(), &registrator_ref)
         .await?;
-    crate::projections::p904_sales_data::repository::delete_by_registrator(&id.to_string()).await?;
+    uow.record_audit(
+        "a010_ozon_fbs_posting",
+        &id.to_string(),
+        DocumentAuditAction::Unposted,
+    )
+    .await?;
+    uow.commit().await?;
 
     tracing::info!("Unposted document a010: {}", id);
     Ok(())
 }
+
+/// Строки p904 без записи в БД (массовая пересборка p904): как при проведении,
+/// движения есть только у DELIVERED.
+pub async fn build_p904_rows(id: Uuid) -> Result<Vec<p904_sales_data::repository::Model>> {
+    let document = repository::get_by_id(id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Document not found: {}", id))?;
+    if document.state.status_norm != "DELIVERED" {
+        return Ok(Vec::new());
+    }
+    p904_sales_data::projection_builder::from_ozon_fbs(&document, &id.to_string()).await
+}
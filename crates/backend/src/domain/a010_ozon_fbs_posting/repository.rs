@@ -6,7 +6,7 @@ use contracts::domain::a010_ozon_fbs_posting::aggregate::{
 };
 use contracts::domain::common::{BaseAggregate, EntityMetadata};
 use sea_orm::entity::prelude::*;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set};
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, Set};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -29,6 +29,7 @@ pub struct Model {
     pub source_meta_json: String,
     pub is_deleted: bool,
     pub is_posted: bool,
+    pub is_sandbox: bool,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
     pub version: i32,
@@ -91,6 +92,7 @@ impl From<Model> for OzonFbsPosting {
             state,
             source_meta,
             is_posted: m.is_posted,
+            is_sandbox: m.is_sandbox,
         }
     }
 }
@@ -158,10 +160,21 @@ pub async fn get_by_document_nos(document_nos: &[String]) -> Result<Vec<OzonFbsP
 
 /// Идемпотентная вставка/обновление по document_no
 pub async fn upsert_document(aggregate: &OzonFbsPosting) -> Result<Uuid> {
+    upsert_document_with_conn(conn(), aggregate).await
+}
+
+pub async fn upsert_document_with_conn<C: ConnectionTrait>(
+    db: &C,
+    aggregate: &OzonFbsPosting,
+) -> Result<Uuid> {
     let uuid = aggregate.base.id.value();
 
     // Проверяем, существует ли документ с таким document_no
-    let existing = get_by_document_no(&aggregate.header.document_no).await?;
+    let existing: Option<OzonFbsPosting> = Entity::find()
+        .filter(Column::DocumentNo.eq(&aggregate.header.document_no))
+        .one(db)
+        .await?
+        .map(Into::into);
 
     let header_json = serde_json::to_string(&aggregate.header)?;
     let lines_json = serde_json::to_string(&aggregate.lines)?;
@@ -185,11 +198,12 @@ pub async fn upsert_document(aggregate: &OzonFbsPosting) -> Result<Uuid> {
             source_meta_json: Set(source_meta_json),
             is_deleted: Set(aggregate.base.metadata.is_deleted),
             is_posted: Set(aggregate.is_posted),
+            is_sandbox: Set(aggregate.is_sandbox),
             updated_at: Set(Some(aggregate.base.metadata.updated_at)),
             version: Set(aggregate.base.metadata.version + 1),
             created_at: sea_orm::ActiveValue::NotSet,
         };
-        active.update(conn()).await?;
+        active.update(db).await?;
         Ok(existing_uuid)
     } else {
         // Вставляем новый документ
@@ -207,11 +221,12 @@ pub async fn upsert_document(aggregate: &OzonFbsPosting) -> Result<Uuid> {
             source_meta_json: Set(source_meta_json),
             is_deleted: Set(aggregate.base.metadata.is_deleted),
             is_posted: Set(aggregate.is_posted),
+            is_sandbox: Set(aggregate.is_sandbox),
             created_at: Set(Some(aggregate.base.metadata.created_at)),
             updated_at: Set(Some(aggregate.base.metadata.updated_at)),
             version: Set(aggregate.base.metadata.version),
         };
-        active.insert(conn()).await?;
+        active.insert(db).await?;
         Ok(uuid)
     }
 }
@@ -0,0 +1,256 @@
+//! Данные экрана сравнения продаж WB (`GET /api/a012/wb-sales/compare`).
+
+use anyhow::Result;
+use chrono::SecondsFormat;
+use contracts::domain::a012_wb_sales::aggregate::WbSales;
+use contracts::domain::a012_wb_sales::compare::{
+    values_differ, CompareValueKind, WbSalesCompareColumn, WbSalesCompareResponse,
+    WbSalesCompareRow,
+};
+use contracts::domain::common::AggregateId;
+use uuid::Uuid;
+
+use super::service;
+use crate::projections::p903_wb_finance_report::repository::{totals_by_srid, SridTotals};
+use crate::shared::marketplaces::links;
+
+/// Документ вместе с итогами его строк финансового отчёта.
+struct CompareSource {
+    sale: WbSales,
+    finance: SridTotals,
+}
+
+fn money(value: Option<f64>) -> Option<String> {
+    value.map(|v| format!("{:.2}", v))
+}
+
+fn text(value: impl ToString) -> Option<String> {
+    Some(value.to_string())
+}
+
+fn yes_no(value: bool) -> Option<String> {
+    text(if value { "Да" } else { "Нет" })
+}
+
+fn build_rows(sources: &[CompareSource]) -> Vec<WbSalesCompareRow> {
+    let mut rows = Vec::new();
+    let mut add = |group: &str,
+                   label: &str,
+                   kind: CompareValueKind,
+                   extract: &dyn Fn(&CompareSource) -> Option<String>| {
+        let values: Vec<Option<String>> = sources.iter().map(extract).collect();
+        rows.push(WbSalesCompareRow {
+            group: group.to_string(),
+            label: label.to_string(),
+            kind,
+            differs: values_differ(&values),
+            values,
+        });
+    };
+
+    add(
+        "Документ",
+        "Дата продажи",
+        CompareValueKind::DateTime,
+        &|s| {
+            text(
+                s.sale
+                    .state
+                    .sale_dt
+                    .to_rfc3339_opts(SecondsFormat::Secs, true),
+            )
+        },
+    );
+    add(
+        "Документ",
+        "Тип события",
+        CompareValueKind::Text,
+        &|s| text(&s.sale.state.event_type),
+    );
+    add(
+        "Документ",
+        "Статус",
+        CompareValueKind::Text,
+        &|s| text(&s.sale.state.status_norm),
+    );
+    add(
+        "Документ",
+        "Возврат",
+        CompareValueKind::Text,
+        &|s| yes_no(s.sale.is_customer_return),
+    );
+    add(
+        "Документ",
+        "Проведён",
+        CompareValueKind::Text,
+        &|s| yes_no(s.sale.is_posted),
+    );
+    add(
+        "Документ",
+        "Склад",
+        CompareValueKind::Text,
+        &|s| s.sale.warehouse.warehouse_name.clone(),
+    );
+    add(
+        "Документ",
+        "Тип склада",
+        CompareValueKind::Text,
+        &|s| s.sale.warehouse.warehouse_type.clone(),
+    );
+    add(
+        "Товар",
+        "Артикул продавца",
+        CompareValueKind::Text,
+        &|s| text(&s.sale.line.supplier_article),
+    );
+    add("Товар", "nm_id", CompareValueKind::Text, &|s| {
+        text(s.sale.line.nm_id)
+    });
+    add(
+        "Товар",
+        "Наименование",
+        CompareValueKind::Text,
+        &|s| text(&s.sale.line.name),
+    );
+    add(
+        "Товар",
+        "Количество",
+        CompareValueKind::Text,
+        &|s| text(s.sale.line.qty),
+    );
+    add(
+        "Суммы",
+        "Цена до скидок",
+        CompareValueKind::Money,
+        &|s| money(s.sale.line.price_list),
+    );
+    add(
+        "Суммы",
+        "Скидка продавца, %",
+        CompareValueKind::Text,
+        &|s| s.sale.line.discount_percent.map(|v| v.to_string()),
+    );
+    add("Суммы", "СПП, %", CompareValueKind::Text, &|s| {
+        s.sale.line.spp.map(|v| v.to_string())
+    });
+    add(
+        "Суммы",
+        "Цена для покупателя",
+        CompareValueKind::Money,
+        &|s| money(s.sale.line.finished_price),
+    );
+    add(
+        "Суммы",
+        "Сумма строки",
+        CompareValueKind::Money,
+        &|s| money(s.sale.line.amount_line),
+    );
+    add(
+        "Суммы",
+        "К перечислению (forPay)",
+        CompareValueKind::Money,
+        &|s| money(s.sale.line.payment_sale_amount),
+    );
+    add(
+        "Суммы",
+        "Комиссия (план)",
+        CompareValueKind::Money,
+        &|s| money(s.sale.line.commission_plan),
+    );
+    add(
+        "Суммы",
+        "Комиссия (факт)",
+        CompareValueKind::Money,
+        &|s| money(s.sale.line.commission_fact),
+    );
+    add(
+        "Суммы",
+        "Эквайринг (план)",
+        CompareValueKind::Money,
+        &|s| money(s.sale.line.acquiring_fee_plan),
+    );
+    add(
+        "Суммы",
+        "Эквайринг (факт)",
+        CompareValueKind::Money,
+        &|s| money(s.sale.line.acquiring_fee_fact),
+    );
+    add(
+        "Суммы",
+        "Выплата продавцу (план)",
+        CompareValueKind::Money,
+        &|s| money(s.sale.line.supplier_payout_plan),
+    );
+    add(
+        "Суммы",
+        "Выплата продавцу (факт)",
+        CompareValueKind::Money,
+        &|s| money(s.sale.line.supplier_payout_fact),
+    );
+    add(
+        "Суммы",
+        "Себестоимость",
+        CompareValueKind::Money,
+        &|s| money(s.sale.line.cost_of_production),
+    );
+    add(
+        "Суммы",
+        "Прибыль (факт)",
+        CompareValueKind::Money,
+        &|s| money(s.sale.line.profit_fact),
+    );
+    add(
+        "Финансовый отчёт (p903)",
+        "Строк отчёта",
+        CompareValueKind::Text,
+        &|s| text(s.finance.count),
+    );
+    add(
+        "Финансовый отчёт (p903)",
+        "К перечислению",
+        CompareValueKind::Money,
+        &|s| money(Some(s.finance.ppvz_for_pay)),
+    );
+    add(
+        "Финансовый отчёт (p903)",
+        "Вознаграждение WB",
+        CompareValueKind::Money,
+        &|s| money(Some(s.finance.ppvz_vw)),
+    );
+    add(
+        "Финансовый отчёт (p903)",
+        "Эквайринг",
+        CompareValueKind::Money,
+        &|s| money(Some(s.finance.acquiring_fee)),
+    );
+
+    rows
+}
+
+/// Загружает документы в порядке `ids` и строит строки сравнения.
+/// `None` — хотя бы один документ не найден.
+pub async fn compare(ids: &[Uuid]) -> Result<Option<WbSalesCompareResponse>> {
+    let mut sources = Vec::with_capacity(ids.len());
+    for id in ids {
+        let Some(sale) = service::get_by_id(*id).await? else {
+            return Ok(None);
+        };
+        let finance = totals_by_srid(&sale.header.document_no, None).await?;
+        sources.push(CompareSource { sale, finance });
+    }
+
+    let columns = sources
+        .iter()
+        .map(|source| WbSalesCompareColumn {
+            id: source.sale.base.id.as_string(),
+            document_no: source.sale.header.document_no.clone(),
+            is_posted: source.sale.is_posted,
+            marketplace_links: links::wb_document_links(source.sale.line.nm_id),
+        })
+        .collect();
+
+    Ok(Some(WbSalesCompareResponse {
+        columns,
+        rows: build_rows(&sources),
+    }))
+}
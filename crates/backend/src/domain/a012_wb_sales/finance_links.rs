@@ -0,0 +1,93 @@
+//! Ручные связи продажи WB со строками финансового отчёта p903
+//! (таблица `a012_wb_sales_finance_links`, ключ строки — `rrd_id`).
+
+use anyhow::{bail, Result};
+use chrono::Utc;
+use contracts::domain::a012_wb_sales::finance_links::MAX_FINANCE_LINKS_PER_REQUEST;
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement, TransactionTrait};
+
+use crate::shared::data::db::get_connection;
+
+/// Сортирует и убирает повторы; пустой или слишком большой список — ошибка запроса.
+pub fn normalize_rrd_ids(mut rrd_ids: Vec<i64>) -> Result<Vec<i64>> {
+    rrd_ids.sort_unstable();
+    rrd_ids.dedup();
+    if rrd_ids.is_empty() {
+        bail!("Не выбраны строки финансового отчёта");
+    }
+    if rrd_ids.len() > MAX_FINANCE_LINKS_PER_REQUEST {
+        bail!(
+            "За один раз можно изменить не больше {} связей",
+            MAX_FINANCE_LINKS_PER_REQUEST
+        );
+    }
+    Ok(rrd_ids)
+}
+
+/// `rrd_id` строк, привязанных к продаже вручную
+pub async fn list_rrd_ids(sale_id: &str) -> Result<Vec<i64>> {
+    let rows = get_connection()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT rrd_id FROM a012_wb_sales_finance_links WHERE sale_id = ? ORDER BY rrd_id",
+            [sale_id.into()],
+        ))
+        .await?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| row.try_get::<i64>("", "rrd_id").ok())
+        .collect())
+}
+
+/// Привязывает строки; уже существующие связи не меняются
+pub async fn attach(sale_id: &str, rrd_ids: &[i64], linked_by: Option<&str>) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    let txn = get_connection().begin().await?;
+    for rrd_id in rrd_ids {
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT OR IGNORE INTO a012_wb_sales_finance_links (sale_id, rrd_id, linked_by, linked_at)
+             VALUES (?, ?, ?, ?)",
+            [
+                sale_id.into(),
+                (*rrd_id).into(),
+                linked_by.map(str::to_string).into(),
+                now.clone().into(),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+pub async fn detach(sale_id: &str, rrd_ids: &[i64]) -> Result<()> {
+    let txn = get_connection().begin().await?;
+    for rrd_id in rrd_ids {
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM a012_wb_sales_finance_links WHERE sale_id = ? AND rrd_id = ?",
+            [sale_id.into(), (*rrd_id).into()],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_sorts_and_dedups() {
+        assert_eq!(normalize_rrd_ids(vec![3, 1, 3, 2]).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn normalize_rejects_empty_and_oversized() {
+        assert!(normalize_rrd_ids(Vec::new()).is_err());
+        let too_many = (0..=MAX_FINANCE_LINKS_PER_REQUEST as i64).collect();
+        assert!(normalize_rrd_ids(too_many).is_err());
+    }
+}
@@ -1,4 +1,6 @@
 pub mod change_token;
+pub mod compare;
+pub mod finance_links;
 pub mod posting;
 pub mod repository;
 pub mod representation;
@@ -1,19 +1,30 @@
 use super::repository;
 use anyhow::Result;
 use chrono::Utc;
+use contracts::domain::a012_wb_sales::aggregate::WbSales;
 use contracts::shared::analytics::TurnoverLayer;
+use contracts::system::document_audit::DocumentAuditAction;
 use sea_orm::TransactionTrait;
+use serde::Serialize;
 use std::collections::HashSet;
 use uuid::Uuid;
 
 use crate::general_ledger::repository::Model as GeneralLedgerModel;
 use crate::general_ledger::turnover_registry::get_turnover_class;
+use crate::projections::p900_mp_sales_register::repository::{
+    Model as P900Model, SalesRegisterEntry,
+};
+use crate::projections::p904_sales_data::repository::Model as P904Model;
 use crate::shared::data::db::get_connection;
+use crate::shared::data::unit_of_work::UnitOfWork;
 use crate::shared::marketplaces::wildberries::datetime::wb_business_date_str;
+use crate::shared::optimistic_lock;
 
 const REGISTRATOR_TYPE: &str = "a012_wb_sales";
 const TURNOVER_CODE_EXPENSE: &str = "advert_clicks_order_expense";
 const RESOURCE_TABLE_P913: &str = "p913_wb_advert_order_attr";
+/// Проекции, о замене строк в которых сообщает лента CDC (их забирает DWH).
+const CDC_PROJECTIONS: &[&str] = &["p900_mp_sales_register", "p904_sales_data"];
 
 fn now_str() -> String {
     Utc::now().to_rfc3339()
@@ -50,15 +61,172 @@ fn to_gl_advert_expense(
     }
 }
 
+/// Строки p900 и p904, которые создаст проведение документа (только чтения, без записи).
+async fn build_sales_projections(
+    document: &WbSales,
+    id_str: &str,
+) -> Result<(SalesRegisterEntry, Vec<P904Model>)> {
+    let p900_entry = crate::projections::p900_mp_sales_register::projection_builder::from_wb_sales(
+        document, id_str,
+    )
+    .await?;
+    // Возврат при включённом взаимозачёте — сторно строк исходной продажи.
+    let p904_entries =
+        match crate::projections::p904_sales_data::return_netting::build_netted_entries(
+            document, id_str,
+        )
+        .await?
+        {
+            Some(entries) => entries,
+            None => {
+                crate::projections::p904_sales_data::projection_builder::from_wb_sales_lines(
+                    document, id_str,
+                )
+                .await?
+            }
+        };
+    Ok((p900_entry, p904_entries))
+}
+
+/// Строки проекций p900/p904 одного документа.
+#[derive(Debug, Serialize)]
+pub struct SalesProjectionRows {
+    pub p900_sales_register: Vec<P900Model>,
+    pub p904_sales_data: Vec<P904Model>,
+}
+
+/// Результат предпросмотра проведения: текущий след документа в проекциях
+/// и строки, которые заменят его после проведения.
+#[derive(Debug, Serialize)]
+pub struct PostingPreview {
+    pub is_posted: bool,
+    pub current: SalesProjectionRows,
+    pub preview: SalesProjectionRows,
+}
+
+/// Документ, подготовленный как при проведении (без записи), и прежний `is_posted`.
+async fn load_prepared(
+    id: Uuid,
+    cache: &mut super::service::PostingPreparationCache,
+) -> Result<(WbSales, bool)> {
+    let mut document = repository::get_by_id(id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Document not found: {}", id))?;
+    let is_posted = document.is_posted;
+
+    super::service::prepare_document_for_posting_cached(&mut document, cache).await?;
+    document.is_customer_return = document.state.event_type.eq_ignore_ascii_case("return")
+        || document.line.finished_price.unwrap_or(0.0) < 0.0;
+    let prod_cost_resolution = super::service::resolve_prod_cost_cached(&document, cache).await?;
+    super::service::apply_prod_cost_diagnostics(&mut document, &prod_cost_resolution);
+    Ok((document, is_posted))
+}
+
+/// Строки p904 документа — как в предпросмотре, только p904 (массовая пересборка в u508).
+pub async fn build_p904_rows(
+    id: Uuid,
+    cache: &mut super::service::PostingPreparationCache,
+) -> Result<Vec<P904Model>> {
+    let (document, _) = load_prepared(id, cache).await?;
+    let (_, p904_entries) = build_sales_projections(&document, &id.to_string()).await?;
+    Ok(p904_entries)
+}
+
+/// Предпросмотр проведения: та же подготовка документа и те же построители строк,
+/// что и в `post_document_with_cache`, но без транзакции — в БД ничего не пишется.
+pub async fn preview_posting(id: Uuid) -> Result<PostingPreview> {
+    let mut cache = super::service::PostingPreparationCache::default();
+    let (document, is_posted) = load_prepared(id, &mut cache).await?;
+
+    let registrator_ref = id.to_string();
+    let (p900_entry, p904_entries) = build_sales_projections(&document, &registrator_ref).await?;
+
+    let current = SalesProjectionRows {
+        p900_sales_register:
+            crate::projections::p900_mp_sales_register::repository::get_by_registrator(
+                &registrator_ref,
+            )
+            .await?,
+        p904_sales_data: crate::projections::p904_sales_data::repository::get_by_registrator(
+            &registrator_ref,
+        )
+        .await?,
+    };
+
+    Ok(PostingPreview {
+        is_posted,
+        current,
+        preview: SalesProjectionRows {
+            p900_sales_register: vec![p900_entry.to_model(now_str())],
+            p904_sales_data: p904_entries,
+        },
+    })
+}
+
 pub async fn post_document(id: Uuid) -> Result<()> {
+    post_document_at_version(id, None).await
+}
+
+/// Проведение из карточки: `expected_version` — версия, которую видит пользователь
+/// (см. `shared::optimistic_lock`); `None` — без проверки.
+pub async fn post_document_at_version(id: Uuid, expected_version: Option<i32>) -> Result<()> {
     let mut cache = super::service::PostingPreparationCache::default();
-    post_document_with_cache(id, &mut cache).await
+    post_document_inner(id, &mut cache, expected_version).await
 }
 
 pub async fn post_document_with_cache(
     id: Uuid,
     cache: &mut super::service::PostingPreparationCache,
 ) -> Result<()> {
+    post_document_inner(id, cache, None).await
+}
+
+async fn post_document_inner(
+    id: Uuid,
+    cache: &mut super::service::PostingPreparationCache,
+    expected_version: Option<i32>,
+) -> Result<()> {
+    let mut prepared = prepare_posting(id, cache).await?;
+
+    // === ФАЗА 2: запись (одна транзакция) ===
+    let mut uow = UnitOfWork::begin().await?;
+    if let Some(expected) = expected_version {
+        optimistic_lock::claim_version(uow.conn(), REGISTRATOR_TYPE, &prepared.id_str, expected)
+            .await?;
+        prepared.document.base.metadata.version = expected;
+    }
+    write_posting(&mut uow, &prepared).await?;
+    uow.commit().await?;
+
+    Ok(())
+}
+
+/// Проведение, собранное в памяти до транзакции: документ и строки всех проекций.
+/// Пишется [`write_posting`] в транзакцию вызывающего — так несколько
+/// документов можно провести одной транзакцией (отмена пакетной операции).
+pub struct PreparedPosting {
+    id: Uuid,
+    document: WbSales,
+    should_persist_document: bool,
+    registrator_ref: String,
+    p909_registrator_ref: String,
+    id_str: String,
+    p900_entry: SalesRegisterEntry,
+    p904_entries: Vec<P904Model>,
+    p919_contribution: Option<crate::projections::p919_daily_sales_summary::contributions::Model>,
+    p909_result:
+        crate::projections::p909_mp_order_line_turnovers::projection_builder::PostingResult,
+    funnel_order_cohort_date: Option<String>,
+    p909_groups: HashSet<(String, String, String)>,
+    p913_gl_entry: Option<GeneralLedgerModel>,
+    p913_expense_entries: Vec<crate::projections::p913_wb_advert_order_attr::repository::Model>,
+}
+
+/// Фаза 1 проведения: чтения и сборка строк, без записи документа и проекций.
+pub async fn prepare_posting(
+    id: Uuid,
+    cache: &mut super::service::PostingPreparationCache,
+) -> Result<PreparedPosting> {
     let mut document = repository::get_by_id(id)
         .await?
         .ok_or_else(|| anyhow::anyhow!("Document not found: {}", id))?;
@@ -101,15 +269,12 @@ pub async fn post_document_with_cache(
         document.before_write();
     }
 
-    let p900_entry = crate::projections::p900_mp_sales_register::projection_builder::from_wb_sales(
-        &document, &id_str,
-    )
-    .await?;
-    let p904_entries =
-        crate::projections::p904_sales_data::projection_builder::from_wb_sales_lines(
-            &document, &id_str,
-        )
-        .await?;
+    let (p900_entry, p904_entries) = build_sales_projections(&document, &id_str).await?;
+    let p919_contribution = crate::projections::p919_daily_sales_summary::builder::from_wb_sales(
+        &document,
+        &registrator_ref,
+        &p904_entries,
+    );
     let p909_result =
         crate::projections::p909_mp_order_line_turnovers::projection_builder::from_wb_sales(
             &document,
@@ -187,72 +352,109 @@ pub async fn post_document_with_cache(
         }
     }
 
-    // === ФАЗА 2: запись (одна транзакция) ===
-    let txn = get_connection().begin().await?;
+    Ok(PreparedPosting {
+        id,
+        document,
+        should_persist_document,
+        registrator_ref,
+        p909_registrator_ref,
+        id_str,
+        p900_entry,
+        p904_entries,
+        p919_contribution,
+        p909_result,
+        funnel_order_cohort_date,
+        p909_groups,
+        p913_gl_entry,
+        p913_expense_entries,
+    })
+}
 
-    if should_persist_document {
-        repository::upsert_document_knowing_existence_with_conn(&txn, &document, Some(id)).await?;
+/// Фаза 2 проведения: документ, проекции, CDC и журнал «История» в транзакции `uow`.
+pub async fn write_posting(uow: &mut UnitOfWork, prepared: &PreparedPosting) -> Result<()> {
+    let PreparedPosting {
+        id,
+        document,
+        should_persist_document,
+        registrator_ref,
+        p909_registrator_ref,
+        id_str,
+        p900_entry,
+        p904_entries,
+        p919_contribution,
+        p909_result,
+        funnel_order_cohort_date,
+        p909_groups,
+        p913_gl_entry,
+        p913_expense_entries,
+    } = prepared;
+    let txn = uow.conn();
+
+    if *should_persist_document {
+        repository::upsert_document_knowing_existence_with_conn(txn, document, Some(*id)).await?;
     }
 
     // Удаляем прежний след документа во всех проекциях.
     crate::projections::p900_mp_sales_register::repository::delete_by_registrator_with_conn(
-        &txn,
-        &registrator_ref,
+        txn,
+        registrator_ref,
     )
     .await?;
     crate::projections::p904_sales_data::repository::delete_by_registrator_with_conn(
-        &txn,
-        &registrator_ref,
+        txn,
+        registrator_ref,
     )
     .await?;
     crate::projections::p909_mp_order_line_turnovers::repository::delete_many_by_registrator_ref_with_conn(
-        &txn,
-        &p909_registrator_ref,
+        txn,
+        p909_registrator_ref,
     )
     .await?;
     crate::general_ledger::repository::delete_by_registrator_with_conn(
-        &txn,
+        txn,
         "a012_wb_sales",
-        &registrator_ref,
+        registrator_ref,
     )
     .await?;
     crate::projections::p913_wb_advert_order_attr::repository::delete_by_registrator_with_conn(
-        &txn,
+        txn,
         REGISTRATOR_TYPE,
-        &registrator_ref,
+        registrator_ref,
     )
     .await?;
     crate::projections::p916_mp_sales_funnel_turnovers::repository::delete_by_registrator_with_conn(
-        &txn,
+        txn,
         crate::projections::p916_mp_sales_funnel_turnovers::builder::REG_A012,
-        &registrator_ref,
+        registrator_ref,
     )
     .await?;
 
     // Вставляем заново собранные строки.
-    crate::projections::p900_mp_sales_register::repository::upsert_entry_with_conn(
-        &txn,
-        &p900_entry,
+    crate::projections::p900_mp_sales_register::repository::upsert_entry_with_conn(txn, p900_entry)
+        .await?;
+    for entry in p904_entries {
+        crate::projections::p904_sales_data::repository::upsert_entry_with_conn(txn, entry).await?;
+    }
+    crate::projections::p919_daily_sales_summary::service::replace_for_registrator_with_conn(
+        txn,
+        registrator_ref,
+        p919_contribution.as_ref(),
     )
     .await?;
-    for entry in &p904_entries {
-        crate::projections::p904_sales_data::repository::upsert_entry_with_conn(&txn, entry)
-            .await?;
-    }
     for turnover in &p909_result.turnovers {
         crate::projections::p909_mp_order_line_turnovers::repository::insert_entry_raw_with_conn(
-            &txn, turnover,
+            txn, turnover,
         )
         .await?;
     }
     crate::general_ledger::repository::insert_entries_bulk_with_conn(
-        &txn,
+        txn,
         &p909_result.general_ledger_entries,
     )
     .await?;
-    for (connection_mp_ref, line_event_key, turnover_code) in &p909_groups {
+    for (connection_mp_ref, line_event_key, turnover_code) in p909_groups {
         crate::projections::p909_mp_order_line_turnovers::repository::refresh_group_link_status_with_conn(
-            &txn,
+            txn,
             connection_mp_ref,
             line_event_key,
             turnover_code,
@@ -260,38 +462,53 @@ pub async fn post_document_with_cache(
         .await?;
     }
 
-    if let Some(gl_entry) = &p913_gl_entry {
-        crate::general_ledger::repository::save_entry_with_conn(&txn, gl_entry).await?;
+    if let Some(gl_entry) = p913_gl_entry {
+        crate::general_ledger::repository::save_entry_with_conn(txn, gl_entry).await?;
     }
-    for entry in &p913_expense_entries {
-        crate::projections::p913_wb_advert_order_attr::repository::save_entry_with_conn(
-            &txn, entry,
-        )
-        .await?;
+    for entry in p913_expense_entries {
+        crate::projections::p913_wb_advert_order_attr::repository::save_entry_with_conn(txn, entry)
+            .await?;
     }
 
     // Стадия 2 воронки p916: выкуп/возврат из a012. Когорта — по дате заказа (a015 по srid),
     // фолбэк на дату продажи, если заказ не найден.
     let p916_rows = crate::projections::p916_mp_sales_funnel_turnovers::builder::from_wb_sales(
-        &document,
-        &registrator_ref,
-        funnel_order_cohort_date,
+        document,
+        registrator_ref,
+        funnel_order_cohort_date.clone(),
     );
     crate::projections::p916_mp_sales_funnel_turnovers::repository::insert_many_with_conn(
-        &txn, &p916_rows,
+        txn, &p916_rows,
     )
     .await?;
 
-    txn.commit().await?;
+    crate::system::cdc::service::record_with_conn(
+        txn,
+        &crate::system::cdc::service::posting_events(
+            REGISTRATOR_TYPE,
+            registrator_ref,
+            CDC_PROJECTIONS,
+            crate::system::cdc::service::ChangeOp::Posted,
+        ),
+    )
+    .await?;
 
-    // Сигнал клиентам обновить открытые списки a012. Бьём внутри _with_cache, чтобы
-    // покрыть все пути проведения (одиночное, batch, repost u508, day-close a033).
-    super::change_token::TOKEN.bump();
+    uow.record_audit(REGISTRATOR_TYPE, id_str, DocumentAuditAction::Posted)
+        .await?;
+
+    // Сигнал клиентам обновить открытые списки a012. Бьём при записи проведения, чтобы
+    // покрыть все пути (одиночное, batch, repost u508, day-close a033, отмена операции).
+    uow.after_commit(|| super::change_token::TOKEN.bump());
 
     Ok(())
 }
 
 pub async fn unpost_document(id: Uuid) -> Result<()> {
+    unpost_document_at_version(id, None).await
+}
+
+/// Отмена проведения из карточки, `expected_version` — как в [`post_document_at_version`].
+pub async fn unpost_document_at_version(id: Uuid, expected_version: Option<i32>) -> Result<()> {
     let mut document = repository::get_by_id(id)
         .await?
         .ok_or_else(|| anyhow::anyhow!("Document not found: {}", id))?;
@@ -331,6 +548,10 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
     // Одна транзакция: обновление документа + снятие всех проекций + пересчёт групп.
     let txn = get_connection().begin().await?;
 
+    if let Some(expected) = expected_version {
+        optimistic_lock::claim_version(&txn, REGISTRATOR_TYPE, &registrator_ref, expected).await?;
+        document.base.metadata.version = expected;
+    }
     repository::upsert_document_knowing_existence_with_conn(&txn, &document, Some(id)).await?;
 
     crate::projections::p900_mp_sales_register::repository::delete_by_registrator_with_conn(
@@ -366,6 +587,12 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
         &registrator_ref,
     )
     .await?;
+    crate::projections::p919_daily_sales_summary::service::replace_for_registrator_with_conn(
+        &txn,
+        &registrator_ref,
+        None,
+    )
+    .await?;
 
     for (connection_mp_ref, line_event_key, turnover_code) in &p909_groups {
         crate::projections::p909_mp_order_line_turnovers::repository::refresh_group_link_status_with_conn(
@@ -377,6 +604,24 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
         .await?;
     }
 
+    crate::system::cdc::service::record_with_conn(
+        &txn,
+        &crate::system::cdc::service::posting_events(
+            REGISTRATOR_TYPE,
+            &registrator_ref,
+            CDC_PROJECTIONS,
+            crate::system::cdc::service::ChangeOp::Unposted,
+        ),
+    )
+    .await?;
+
+    crate::system::document_audit::record_with_conn(
+        &txn,
+        REGISTRATOR_TYPE,
+        &id.to_string(),
+        DocumentAuditAction::Unposted,
+    )
+    .await?;
     txn.commit().await?;
 
     // Сигнал клиентам обновить открытые списки a012.
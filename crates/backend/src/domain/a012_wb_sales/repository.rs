@@ -11,8 +11,13 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::shared::calc_column::CalcSelect;
 use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+use crate::shared::data::unit_of_work::UnitOfWork;
 use crate::shared::marketplaces::wildberries::datetime::format_wb_local_datetime_seconds;
+use crate::shared::quick_filter::QuickFilterColumn;
+use crate::system::cdc::service::{ChangeEvent, ChangeOp};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "a012_wb_sales")]
@@ -99,6 +104,7 @@ pub struct Model {
     pub nomenclature_ref: Option<String>,
     pub is_deleted: bool,
     pub is_posted: bool,
+    pub is_sandbox: bool,
     pub is_customer_return: bool,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
@@ -169,6 +175,7 @@ impl From<Model> for WbSales {
             warehouse,
             source_meta,
             is_posted: m.is_posted,
+            is_sandbox: m.is_sandbox,
             is_customer_return: m.is_customer_return,
             marketplace_product_ref: m.marketplace_product_ref,
             nomenclature_ref: m.nomenclature_ref,
@@ -180,13 +187,16 @@ impl From<Model> for WbSales {
     }
 }
 
+/// Таблица документов; старые месяцы лежат в архиве (см. [`projection_archive`]).
+const TABLE: &str = "a012_wb_sales";
+
 fn conn() -> &'static DatabaseConnection {
     get_connection()
 }
 
 pub async fn list_all() -> Result<Vec<WbSales>> {
-    let items: Vec<WbSales> = Entity::find()
-        .filter(Column::IsDeleted.eq(false))
+    let query = Entity::find().filter(Column::IsDeleted.eq(false));
+    let items: Vec<WbSales> = projection_archive::route_select(TABLE, None, query)
         .all(conn())
         .await?
         .into_iter()
@@ -211,14 +221,19 @@ pub async fn list_by_sale_date_range(
         query = query.filter(Column::SaleDate.lte(format!("{}T23:59:59", to.format("%Y-%m-%d"))));
     }
 
-    let items = query.all(conn()).await?;
+    let date_from = date_from.map(|from| from.format("%Y-%m-%d").to_string());
+    let items = projection_archive::route_select(TABLE, date_from.as_deref(), query)
+        .all(conn())
+        .await?;
     Ok(items.into_iter().map(Into::into).collect())
 }
 
+/// id продаж периода; с `organization_id` — только этой организации.
 pub async fn list_ids_by_sale_date_range(
     date_from: &str,
     date_to: &str,
     only_posted: bool,
+    organization_id: Option<&str>,
 ) -> Result<Vec<String>> {
     let date_to_end = format!("{}T23:59:59", date_to);
     let posted_clause = if only_posted {
@@ -226,9 +241,150 @@ pub async fn list_ids_by_sale_date_range(
     } else {
         ""
     };
+    let organization_clause = if organization_id.is_some() {
+        " AND LOWER(TRIM(REPLACE(COALESCE(organization_id, ''), '\"', ''))) = LOWER(TRIM(REPLACE(?, '\"', '')))"
+    } else {
+        ""
+    };
+    let sql = format!(
+        "SELECT id FROM {} WHERE is_deleted = 0 AND sale_date IS NOT NULL AND sale_date >= ? AND sale_date <= ?{}{}",
+        projection_archive::source(TABLE, Some(date_from)),
+        posted_clause,
+        organization_clause
+    );
+    let mut values: Vec<sea_orm::Value> = vec![date_from.into(), date_to_end.into()];
+    if let Some(organization_id) = organization_id {
+        values.push(organization_id.into());
+    }
+    let stmt = Statement::from_sql_and_values(conn().get_database_backend(), &sql, values);
+    let rows = conn().query_all(stmt).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| row.try_get::<String>("", "id").unwrap_or_default())
+        .collect())
+}
+
+/// Продажи товара (`nm_id`) в кабинете: штук за окно и дата последней продажи.
+#[derive(Debug, Clone)]
+pub struct NmSalesVelocityRow {
+    pub connection_id: String,
+    pub nm_id: i64,
+    /// Продано штук начиная с `window_from` (возвраты не вычитаются).
+    pub window_qty: f64,
+    /// Последняя продажа за всю историю, `YYYY-MM-DD`.
+    pub last_sale_date: Option<String>,
+}
+
+/// Скорость продаж по `nm_id` для складских алертов: только `event_type = 'sale'`.
+pub async fn sales_velocity_by_nm_id(window_from: &str) -> Result<Vec<NmSalesVelocityRow>> {
+    let sql = format!(
+        "SELECT connection_id, nm_id, \
+                SUM(CASE WHEN sale_date >= ? THEN COALESCE(qty, 0) ELSE 0 END) AS window_qty, \
+                MAX(substr(sale_date, 1, 10)) AS last_sale_date \
+         FROM {} \
+         WHERE is_deleted = 0 \
+           AND event_type = 'sale' \
+           AND nm_id IS NOT NULL \
+           AND sale_date IS NOT NULL \
+           AND connection_id IS NOT NULL \
+           AND connection_id <> '' \
+         GROUP BY connection_id, nm_id",
+        projection_archive::source(TABLE, None)
+    );
+    let stmt =
+        Statement::from_sql_and_values(conn().get_database_backend(), &sql, [window_from.into()]);
+    let rows = conn().query_all(stmt).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| NmSalesVelocityRow {
+            connection_id: row.try_get("", "connection_id").unwrap_or_default(),
+            nm_id: row.try_get("", "nm_id").unwrap_or_default(),
+            window_qty: row.try_get("", "window_qty").unwrap_or_default(),
+            last_sale_date: row.try_get("", "last_sale_date").ok(),
+        })
+        .collect())
+}
+
+/// Исходная продажа для возврата: та же пара (srid, артикул), `event_type = 'sale'`.
+#[derive(Debug, Clone)]
+pub struct SaleForReturnRow {
+    pub id: String,
+    pub is_posted: bool,
+    pub qty: f64,
+}
+
+/// Найти продажу, которую отменяет возврат (предпочтительно проведённую).
+pub async fn find_sale_for_return(
+    document_no: &str,
+    supplier_article: &str,
+) -> Result<Option<SaleForReturnRow>> {
     let sql = format!(
-        "SELECT id FROM a012_wb_sales WHERE is_deleted = 0 AND sale_date IS NOT NULL AND sale_date >= ? AND sale_date <= ?{}",
-        posted_clause
+        "SELECT id, is_posted, COALESCE(qty, 0) AS qty \
+         FROM {} \
+         WHERE document_no = ? \
+           AND supplier_article = ? \
+           AND event_type = 'sale' \
+           AND is_deleted = 0 \
+         ORDER BY is_posted DESC \
+         LIMIT 1",
+        projection_archive::source(TABLE, None)
+    );
+    let stmt = Statement::from_sql_and_values(
+        conn().get_database_backend(),
+        &sql,
+        [document_no.into(), supplier_article.into()],
+    );
+    let Some(row) = conn().query_one(stmt).await? else {
+        return Ok(None);
+    };
+    Ok(Some(SaleForReturnRow {
+        id: row.try_get("", "id")?,
+        is_posted: row.try_get("", "is_posted").unwrap_or(false),
+        qty: row.try_get("", "qty").unwrap_or_default(),
+    }))
+}
+
+/// Проведённый возврат без проведённой продажи по той же паре (srid, артикул).
+#[derive(Debug, Clone)]
+pub struct UnmatchedReturnRow {
+    pub id: String,
+    pub document_no: String,
+    pub sale_date: String,
+    pub supplier_article: String,
+    pub connection_id: String,
+    pub finished_price: Option<f64>,
+    /// Продажа есть, но не проведена.
+    pub sale_exists: bool,
+}
+
+pub async fn list_unmatched_returns(
+    date_from: &str,
+    date_to: &str,
+) -> Result<Vec<UnmatchedReturnRow>> {
+    let date_to_end = format!("{}T23:59:59", date_to);
+    // Продажа могла уйти в архив раньше возврата, поэтому ищется без границы периода.
+    let sales = projection_archive::source(TABLE, None);
+    let returns = projection_archive::source(TABLE, Some(date_from));
+    let sql = format!(
+        "SELECT r.id, r.document_no, substr(r.sale_date, 1, 10) AS sale_date, \
+                r.supplier_article, r.connection_id, r.finished_price, \
+                EXISTS (SELECT 1 FROM {sales} s \
+                        WHERE s.document_no = r.document_no \
+                          AND s.supplier_article = r.supplier_article \
+                          AND s.event_type = 'sale' AND s.is_deleted = 0) AS sale_exists \
+         FROM {returns} r \
+         WHERE r.event_type = 'return' \
+           AND r.is_deleted = 0 \
+           AND r.is_posted = 1 \
+           AND r.sale_date >= ? \
+           AND r.sale_date <= ? \
+           AND NOT EXISTS (SELECT 1 FROM {sales} s \
+                           WHERE s.document_no = r.document_no \
+                             AND s.supplier_article = r.supplier_article \
+                             AND s.event_type = 'sale' \
+                             AND s.is_deleted = 0 \
+                             AND s.is_posted = 1) \
+         ORDER BY r.sale_date, r.document_no"
     );
     let stmt = Statement::from_sql_and_values(
         conn().get_database_backend(),
@@ -238,7 +394,15 @@ pub async fn list_ids_by_sale_date_range(
     let rows = conn().query_all(stmt).await?;
     Ok(rows
         .into_iter()
-        .map(|row| row.try_get::<String>("", "id").unwrap_or_default())
+        .map(|row| UnmatchedReturnRow {
+            id: row.try_get("", "id").unwrap_or_default(),
+            document_no: row.try_get("", "document_no").unwrap_or_default(),
+            sale_date: row.try_get("", "sale_date").unwrap_or_default(),
+            supplier_article: row.try_get("", "supplier_article").unwrap_or_default(),
+            connection_id: row.try_get("", "connection_id").unwrap_or_default(),
+            finished_price: row.try_get("", "finished_price").ok(),
+            sale_exists: row.try_get("", "sale_exists").unwrap_or(false),
+        })
         .collect())
 }
 
@@ -248,6 +412,8 @@ pub struct RepostChunkKey {
     pub connection_mp_ref: String,
 }
 
+/// Пары (день продажи, кабинет) для перепроведения за период; документы
+/// песочницы (`is_sandbox`) не перепроводятся.
 pub async fn list_repost_chunks_by_sale_date_range(
     date_from: &str,
     date_to: &str,
@@ -255,15 +421,17 @@ pub async fn list_repost_chunks_by_sale_date_range(
     connection_mp_refs: &[String],
 ) -> Result<Vec<RepostChunkKey>> {
     let date_to_end = format!("{}T23:59:59", date_to);
-    let mut sql = String::from(
+    let mut sql = format!(
         "SELECT substr(sale_date, 1, 10) AS sale_date, connection_id AS connection_mp_ref \
-         FROM a012_wb_sales \
+         FROM {} \
          WHERE is_deleted = 0 \
+           AND is_sandbox = 0 \
            AND sale_date IS NOT NULL \
            AND sale_date >= ? \
            AND sale_date <= ? \
            AND connection_id IS NOT NULL \
            AND connection_id <> ''",
+        projection_archive::source(TABLE, Some(date_from))
     );
 
     let mut params = vec![date_from.into(), date_to_end.into()];
@@ -303,12 +471,14 @@ pub async fn list_ids_by_sale_date_and_connection(
     connection_mp_ref: &str,
     only_posted: bool,
 ) -> Result<Vec<String>> {
-    let mut sql = String::from(
-        "SELECT id FROM a012_wb_sales \
+    let mut sql = format!(
+        "SELECT id FROM {} \
          WHERE is_deleted = 0 \
+           AND is_sandbox = 0 \
            AND sale_date IS NOT NULL \
            AND substr(sale_date, 1, 10) = ? \
            AND connection_id = ?",
+        projection_archive::source(TABLE, Some(sale_date))
     );
     if only_posted {
         sql.push_str(" AND is_posted = 1");
@@ -328,7 +498,10 @@ pub async fn list_ids_by_sale_date_and_connection(
 }
 
 pub async fn get_by_id(id: Uuid) -> Result<Option<WbSales>> {
-    let result = Entity::find_by_id(id.to_string()).one(conn()).await?;
+    let query = Entity::find_by_id(id.to_string());
+    let result = projection_archive::route_select(TABLE, None, query)
+        .one(conn())
+        .await?;
     Ok(result.map(Into::into))
 }
 
@@ -337,16 +510,16 @@ pub async fn list_by_ids(ids: &[String]) -> Result<Vec<WbSales>> {
         return Ok(Vec::new());
     }
 
-    let items = Entity::find()
-        .filter(Column::Id.is_in(ids.iter().cloned()))
+    let query = Entity::find().filter(Column::Id.is_in(ids.iter().cloned()));
+    let items = projection_archive::route_select(TABLE, None, query)
         .all(conn())
         .await?;
     Ok(items.into_iter().map(Into::into).collect())
 }
 
 pub async fn get_by_document_no(document_no: &str) -> Result<Option<WbSales>> {
-    let result = Entity::find()
-        .filter(Column::DocumentNo.eq(document_no))
+    let query = Entity::find().filter(Column::DocumentNo.eq(document_no));
+    let result = projection_archive::route_select(TABLE, None, query)
         .one(conn())
         .await?;
     Ok(result.map(Into::into))
@@ -354,8 +527,8 @@ pub async fn get_by_document_no(document_no: &str) -> Result<Option<WbSales>> {
 
 /// Get by sale_id (saleID from WB API) - used for deduplication
 pub async fn get_by_sale_id(sale_id: &str) -> Result<Option<WbSales>> {
-    let result = Entity::find()
-        .filter(Column::SaleId.eq(sale_id))
+    let query = Entity::find().filter(Column::SaleId.eq(sale_id));
+    let result = projection_archive::route_select(TABLE, None, query)
         .one(conn())
         .await?;
     Ok(result.map(Into::into))
@@ -375,7 +548,8 @@ pub async fn list_existing_sale_ids(sale_ids: &[String]) -> Result<HashMap<Strin
             .collect::<Vec<_>>()
             .join(", ");
         let sql = format!(
-            "SELECT id, sale_id FROM a012_wb_sales WHERE sale_id IN ({}) AND is_deleted = 0",
+            "SELECT id, sale_id FROM {} WHERE sale_id IN ({}) AND is_deleted = 0",
+            projection_archive::source(TABLE, None),
             placeholders
         );
         let params: Vec<sea_orm::Value> = chunk.iter().map(|id| id.clone().into()).collect();
@@ -397,10 +571,11 @@ pub async fn get_by_composite_key(
     event_type: &str,
     supplier_article: &str,
 ) -> Result<Option<WbSales>> {
-    let result = Entity::find()
+    let query = Entity::find()
         .filter(Column::DocumentNo.eq(document_no))
         .filter(Column::EventType.eq(event_type))
-        .filter(Column::SupplierArticle.eq(supplier_article))
+        .filter(Column::SupplierArticle.eq(supplier_article));
+    let result = projection_archive::route_select(TABLE, None, query)
         .one(conn())
         .await?;
     Ok(result.map(Into::into))
@@ -497,6 +672,7 @@ pub async fn upsert_document_knowing_existence_with_conn<C: ConnectionTrait>(
             existing_uuid,
             sale_id
         );
+        projection_archive::restore_with_conn(db, TABLE, &existing_uuid.to_string()).await?;
         let active = ActiveModel {
             id: Set(existing_uuid.to_string()),
             code: Set(aggregate.base.code.clone()),
@@ -547,12 +723,14 @@ pub async fn upsert_document_knowing_existence_with_conn<C: ConnectionTrait>(
             nomenclature_ref: Set(aggregate.nomenclature_ref.clone()),
             is_deleted: Set(aggregate.base.metadata.is_deleted),
             is_posted: Set(aggregate.is_posted),
+            is_sandbox: Set(aggregate.is_sandbox),
             is_customer_return: Set(aggregate.is_customer_return),
             updated_at: Set(Some(aggregate.base.metadata.updated_at)),
             version: Set(aggregate.base.metadata.version + 1),
             created_at: sea_orm::ActiveValue::NotSet,
         };
         active.update(db).await?;
+        record_change(db, existing_uuid, ChangeOp::Updated).await?;
         Ok(existing_uuid)
     } else {
         tracing::debug!(
@@ -611,6 +789,7 @@ pub async fn upsert_document_knowing_existence_with_conn<C: ConnectionTrait>(
             nomenclature_ref: Set(aggregate.nomenclature_ref.clone()),
             is_deleted: Set(aggregate.base.metadata.is_deleted),
             is_posted: Set(aggregate.is_posted),
+            is_sandbox: Set(aggregate.is_sandbox),
             is_customer_return: Set(aggregate.is_customer_return),
             created_at: Set(Some(aggregate.base.metadata.created_at)),
             updated_at: Set(Some(aggregate.base.metadata.updated_at)),
@@ -618,25 +797,61 @@ pub async fn upsert_document_knowing_existence_with_conn<C: ConnectionTrait>(
         };
 
         active.insert(db).await?;
+        record_change(db, uuid, ChangeOp::Created).await?;
         Ok(uuid)
     }
 }
 
+/// Событие ленты CDC в той же транзакции, что и запись документа.
+async fn record_change<C: ConnectionTrait>(db: &C, id: Uuid, op: ChangeOp) -> Result<()> {
+    crate::system::cdc::service::record_with_conn(
+        db,
+        &[ChangeEvent::aggregate("a012_wb_sales", id.to_string(), op)],
+    )
+    .await
+}
+
 pub async fn soft_delete(id: Uuid) -> Result<bool> {
     use sea_orm::sea_query::Expr;
+    let uow = UnitOfWork::begin().await?;
+    projection_archive::restore_with_conn(uow.conn(), TABLE, &id.to_string()).await?;
     let result = Entity::update_many()
         .col_expr(Column::IsDeleted, Expr::value(true))
         .col_expr(Column::UpdatedAt, Expr::value(Utc::now()))
         .filter(Column::Id.eq(id.to_string()))
-        .exec(conn())
+        .exec(uow.conn())
         .await?;
+    if result.rows_affected > 0 {
+        record_change(uow.conn(), id, ChangeOp::Deleted).await?;
+    }
+    uow.commit().await?;
     Ok(result.rows_affected > 0)
 }
 
+/// Id документов, помеченных удалёнными начиная с `since` (для дельты списка).
+pub async fn deleted_ids_since(since: &chrono::DateTime<Utc>) -> Result<Vec<String>> {
+    let sql = format!(
+        "SELECT id FROM {} WHERE is_deleted = 1 AND {}",
+        projection_archive::source(TABLE, None),
+        crate::shared::delta::changed_since_sql("updated_at", since)
+    );
+    let rows = conn()
+        .query_all(Statement::from_string(
+            sea_orm::DatabaseBackend::Sqlite,
+            sql,
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| row.try_get::<String>("", "id").ok())
+        .collect())
+}
+
 pub async fn search_by_document_no(document_no: &str) -> Result<Vec<WbSales>> {
-    let items: Vec<WbSales> = Entity::find()
+    let query = Entity::find()
         .filter(Column::DocumentNo.eq(document_no))
-        .filter(Column::IsDeleted.eq(false))
+        .filter(Column::IsDeleted.eq(false));
+    let items: Vec<WbSales> = projection_archive::route_select(TABLE, None, query)
         .all(conn())
         .await?
         .into_iter()
@@ -660,11 +875,12 @@ pub async fn list_ids_by_connection_and_document_nos(
     for chunk in document_nos.chunks(CHUNK) {
         let placeholders = chunk.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
         let sql = format!(
-            "SELECT id FROM a012_wb_sales \
+            "SELECT id FROM {source} \
              WHERE is_deleted = 0 \
                AND connection_id = ? \
                AND substr(COALESCE(sale_date, ''), 1, 10) <= ? \
-               AND document_no IN ({placeholders})"
+               AND document_no IN ({placeholders})",
+            source = projection_archive::source(TABLE, None)
         );
         let mut params: Vec<sea_orm::Value> = vec![connection_id.into(), sale_date_to.into()];
         params.extend(chunk.iter().map(|s| s.as_str().into()));
@@ -700,11 +916,12 @@ pub async fn list_ids_by_document_nos_since(
     for chunk in document_nos.chunks(CHUNK) {
         let placeholders = chunk.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
         let mut sql = format!(
-            "SELECT id FROM a012_wb_sales \
+            "SELECT id FROM {source} \
              WHERE is_deleted = 0 \
                AND sale_date IS NOT NULL \
                AND substr(sale_date, 1, 10) >= ? \
-               AND document_no IN ({placeholders})"
+               AND document_no IN ({placeholders})",
+            source = projection_archive::source(TABLE, Some(date_from))
         );
         if only_posted {
             sql.push_str(" AND is_posted = 1");
@@ -764,8 +981,60 @@ pub struct WbSalesListRow {
     pub prod_cost_status: Option<String>,
     pub prod_cost_problem_message: Option<String>,
     pub prod_cost_resolved_total: Option<f64>,
+    /// Значения вычисляемых колонок (порядок — как в [`WbSalesListQuery::calc`])
+    pub calc_values: Vec<Option<f64>>,
 }
 
+/// SQL-выражения полей вычисляемых колонок (`contracts::domain::a012_wb_sales::calc_fields`)
+pub const CALC_COLUMNS: &[(&str, &str)] = &[
+    ("qty", "s.qty"),
+    ("amount_line", "s.amount_line"),
+    ("total_price", "s.total_price"),
+    ("finished_price", "s.finished_price"),
+    ("dealer_price_ut", "s.dealer_price_ut"),
+    ("cost_of_production", "s.cost_of_production"),
+    ("sell_out_plan", "s.sell_out_plan"),
+    ("sell_out_fact", "s.sell_out_fact"),
+    ("commission_plan", "s.commission_plan"),
+    ("commission_fact", "s.commission_fact"),
+    ("acquiring_fee_plan", "s.acquiring_fee_plan"),
+    ("acquiring_fee_fact", "s.acquiring_fee_fact"),
+    ("other_fee_plan", "s.other_fee_plan"),
+    ("other_fee_fact", "s.other_fee_fact"),
+    ("supplier_payout_plan", "s.supplier_payout_plan"),
+    ("supplier_payout_fact", "s.supplier_payout_fact"),
+    ("profit_plan", "s.profit_plan"),
+    ("profit_fact", "s.profit_fact"),
+];
+
+/// SQL-выражения полей быстрого фильтра списка (`contracts::domain::a012_wb_sales::quick_filter`)
+pub const QUICK_FILTER_COLUMNS: &[(&str, QuickFilterColumn)] = &[
+    (
+        "status",
+        QuickFilterColumn::Cases(&[
+            ("posted", "s.is_posted = 1"),
+            ("unposted", "s.is_posted = 0"),
+        ]),
+    ),
+    (
+        "type",
+        QuickFilterColumn::Cases(&[
+            ("sale", "LOWER(COALESCE(s.event_type, '')) <> 'return'"),
+            ("return", "LOWER(COALESCE(s.event_type, '')) = 'return'"),
+        ]),
+    ),
+    (
+        "warehouse",
+        QuickFilterColumn::Expr("json_extract(s.warehouse_json, '$.warehouse_name')"),
+    ),
+    ("article", QuickFilterColumn::Expr("s.supplier_article")),
+    ("srid", QuickFilterColumn::Expr("s.document_no")),
+    ("nm", QuickFilterColumn::Expr("s.nm_id")),
+    ("amount", QuickFilterColumn::Expr("s.amount_line")),
+    ("qty", QuickFilterColumn::Expr("s.qty")),
+    ("date", QuickFilterColumn::Expr("s.sale_date")),
+];
+
 /// Query parameters for list
 #[derive(Debug, Clone)]
 pub struct WbSalesListQuery {
@@ -775,6 +1044,14 @@ pub struct WbSalesListQuery {
     pub search_sale_id: Option<String>,
     pub search_srid: Option<String>,
     pub search_supplier_article: Option<String>,
+    /// Условия быстрого фильтра (см. [`QUICK_FILTER_COLUMNS`])
+    pub quick_conditions: Vec<String>,
+    /// Свободный поиск быстрого фильтра: SRID, sale_id, артикул, наименование
+    pub search_text: Option<String>,
+    /// Вычисляемые колонки (см. [`CALC_COLUMNS`])
+    pub calc: CalcSelect,
+    /// Режим дельты: только строки, изменённые начиная с этого момента
+    pub changed_since: Option<chrono::DateTime<Utc>>,
     pub sort_by: String,
     pub sort_desc: bool,
     pub limit: usize,
@@ -836,12 +1113,27 @@ pub async fn list_sql(query: WbSalesListQuery) -> Result<WbSalesListResult> {
             ));
         }
     }
+    if let Some(ref search_text) = query.search_text {
+        conditions.push(format!(
+            "(s.document_no LIKE '%{0}%' OR s.sale_id LIKE '%{0}%' OR \
+             s.supplier_article LIKE '%{0}%' OR s.product_name LIKE '%{0}%')",
+            search_text.replace('\'', "''")
+        ));
+    }
+    conditions.extend(query.quick_conditions.iter().cloned());
+    if let Some(ref since) = query.changed_since {
+        conditions.push(crate::shared::delta::changed_since_sql(
+            "s.updated_at",
+            since,
+        ));
+    }
 
     let where_clause = conditions.join(" AND ");
+    let source = projection_archive::source(TABLE, query.date_from.as_deref());
 
     // Count total
     let count_sql = format!(
-        "SELECT COUNT(*) as cnt FROM a012_wb_sales s WHERE {}",
+        "SELECT COUNT(*) as cnt FROM {source} s WHERE {}",
         where_clause
     );
     let count_result = db
@@ -884,8 +1176,8 @@ pub async fn list_sql(query: WbSalesListQuery) -> Result<WbSalesListResult> {
             s.other_fee_plan, s.other_fee_fact, s.supplier_payout_plan, s.supplier_payout_fact,
             s.profit_plan, s.profit_fact, s.cost_of_production, s.commission_plan, s.commission_fact,
             s.dealer_price_ut, s.prod_cost_problem, s.prod_cost_status,
-            s.prod_cost_problem_message, s.prod_cost_resolved_total
-        FROM a012_wb_sales s
+            s.prod_cost_problem_message, s.prod_cost_resolved_total{}
+        FROM {source} s
         LEFT JOIN a002_organization org
                ON LOWER(TRIM(REPLACE(COALESCE(org.id, ''), '\"', '')))
                 = LOWER(TRIM(REPLACE(COALESCE(s.organization_id, ''), '\"', '')))
@@ -893,7 +1185,12 @@ pub async fn list_sql(query: WbSalesListQuery) -> Result<WbSalesListResult> {
         WHERE {}
         ORDER BY {} {} NULLS LAST
         LIMIT {} OFFSET {}"#,
-        where_clause, order_column, order_dir, query.limit, query.offset
+        query.calc.select_sql(),
+        where_clause,
+        order_column,
+        order_dir,
+        query.limit,
+        query.offset
     );
 
     let rows = db
@@ -948,6 +1245,7 @@ pub async fn list_sql(query: WbSalesListQuery) -> Result<WbSalesListResult> {
                 prod_cost_status: row.try_get("", "prod_cost_status").ok(),
                 prod_cost_problem_message: row.try_get("", "prod_cost_problem_message").ok(),
                 prod_cost_resolved_total: row.try_get("", "prod_cost_resolved_total").ok(),
+                calc_values: query.calc.read(&row),
             })
         })
         .collect();
@@ -4,30 +4,51 @@ use std::collections::HashMap;
 
 use contracts::domain::a012_wb_sales::ENTITY_METADATA;
 use contracts::general_ledger::AggregateRepresentation;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect};
+use sea_orm::{
+    ColumnTrait, DatabaseBackend, EntityTrait, FromQueryResult, QueryFilter, QuerySelect,
+    QueryTrait,
+};
 
 use super::repository::{Column, Entity};
 use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
 use crate::shared::representation::{build, chunked};
 
+#[derive(FromQueryResult)]
+struct ReprRow {
+    id: String,
+    sale_date: Option<String>,
+    document_no: String,
+}
+
 /// Батч-резолв представлений: название типа + дата продажи + номер документа.
+/// Документ может лежать в архиве, поэтому запрос идёт через [`projection_archive`].
 pub async fn represent_many(ids: &[String]) -> HashMap<String, AggregateRepresentation> {
     chunked(ids, |chunk| async move {
-        let rows = Entity::find()
+        let select = Entity::find()
             .select_only()
             .column(Column::Id)
             .column(Column::SaleDate)
             .column(Column::DocumentNo)
-            .filter(Column::Id.is_in(chunk))
-            .into_tuple::<(String, Option<String>, String)>()
+            .filter(Column::Id.is_in(chunk));
+        let stmt = projection_archive::route_statement(
+            "a012_wb_sales",
+            None,
+            select.build(DatabaseBackend::Sqlite),
+        );
+        let rows = ReprRow::find_by_statement(stmt)
             .all(get_connection())
             .await
             .unwrap_or_default();
         rows.into_iter()
-            .map(|(id, date, doc_no)| {
+            .map(|row| {
                 (
-                    id,
-                    build(ENTITY_METADATA.ui.element_name, date, Some(doc_no)),
+                    row.id,
+                    build(
+                        ENTITY_METADATA.ui.element_name,
+                        row.sale_date,
+                        Some(row.document_no),
+                    ),
                 )
             })
             .collect()
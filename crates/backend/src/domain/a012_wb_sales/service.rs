@@ -1078,6 +1078,17 @@ pub async fn store_document_with_raw_shared_cache(
                 e
             );
         }
+        if let Err(e) =
+            crate::projections::p919_daily_sales_summary::service::remove_for_registrator(
+                &id.to_string(),
+            )
+            .await
+        {
+            tracing::error!(
+                "Failed to remove WB Sales document from P919 summary: {}",
+                e
+            );
+        }
     }
 
     Ok((id, is_new))
@@ -1,16 +1,15 @@
 use super::repository;
 use super::service::auto_fill_references;
 use anyhow::Result;
-use sea_orm::TransactionTrait;
+use contracts::domain::a013_ym_order::aggregate::YmOrder;
+use contracts::system::document_audit::DocumentAuditAction;
 use uuid::Uuid;
 
-/// Провести документ (установить is_posted = true и создать проекции)
-/// При проведении автоматически заполняются:
-/// - marketplace_product_ref (поиск или создание в a007_marketplace_product)
-/// - nomenclature_ref (из соответствия в a007_marketplace_product)
-/// - is_error (ненулевой если есть строки без nomenclature_ref)
-/// - Недостающие поля (creation_date, delivery_date и т.д.) из raw JSON для старых документов
-pub async fn post_document(id: Uuid) -> Result<()> {
+use crate::projections::{p900_mp_sales_register, p904_sales_data, p915_mp_order_events};
+use crate::shared::data::unit_of_work::UnitOfWork;
+
+/// Загрузить документ и подготовить к проведению (без записи в БД).
+async fn load_prepared(id: Uuid) -> Result<YmOrder> {
     // Загрузить документ (с полными строками из items table)
     let mut document = repository::get_by_id_with_items(id)
         .await?
@@ -34,56 +33,102 @@ pub async fn post_document(id: Uuid) -> Result<()> {
     // Расчёт итоговой дилерской суммы и маржи документа
     super::service::calculate_totals_and_margin(&mut document).await?;
 
-    // Установить флаг is_posted
-    document.is_posted = true;
-    document.before_write();
-
-    // Сохранить документ (включая обновлённые строки в items table)
-    repository::upsert_document(&document).await?;
-
-    // Удалить старые проекции (если были)
-    crate::projections::p900_mp_sales_register::service::delete_by_registrator(&id.to_string())
-        .await?;
-    crate::projections::p904_sales_data::repository::delete_by_registrator(&id.to_string()).await?;
+    Ok(document)
+}
 
-    // Создать новые проекции
-    crate::projections::p900_mp_sales_register::service::project_ym_order(&document, id).await?;
-    crate::projections::p904_sales_data::service::project_ym_order(&document, id).await?;
+/// Провести документ (установить is_posted = true и создать проекции)
+/// При проведении автоматически заполняются:
+/// - marketplace_product_ref (поиск или создание в a007_marketplace_product)
+/// - nomenclature_ref (из соответствия в a007_marketplace_product)
+/// - is_error (ненулевой если есть строки без nomenclature_ref)
+/// - Недостающие поля (creation_date, delivery_date и т.д.) из raw JSON для старых документов
+pub async fn post_document(id: Uuid) -> Result<()> {
+    let prepared = prepare_posting(id).await?;
 
-    // Таймлайн событий заказа (p915): дата заказа / дата доставки.
-    // a013 проводится без внешней транзакции — открываем локальную для p915.
-    {
-        let registrator_ref = id.to_string();
-        let db = crate::shared::data::db::get_connection();
-        let txn = db.begin().await?;
-        crate::projections::p915_mp_order_events::repository::delete_by_registrator_with_conn(
-            &txn,
-            "a013_ym_order",
-            &registrator_ref,
-        )
-        .await?;
-        let order_events = crate::projections::p915_mp_order_events::builder::from_ym_order(
-            &document,
-            &registrator_ref,
-        );
-        for event in &order_events {
-            crate::projections::p915_mp_order_events::repository::insert_entry_raw_with_conn(
-                &txn, event,
-            )
-            .await?;
-        }
-        txn.commit().await?;
-    }
+    let mut uow = UnitOfWork::begin().await?;
+    write_posting(&mut uow, &prepared).await?;
+    uow.commit().await?;
 
     tracing::info!(
         "Posted document a013: {}, is_error: {}",
         id,
-        document.is_error
+        prepared.document.is_error
     );
 
-    // Сигнал клиентам обновить открытые списки a013.
-    super::change_token::TOKEN.bump();
+    Ok(())
+}
+
+/// Проведение, собранное до транзакции: документ и строки p900, p904, p915.
+pub struct PreparedPosting {
+    id: Uuid,
+    document: YmOrder,
+    p900_entries: Vec<p900_mp_sales_register::repository::SalesRegisterEntry>,
+    p904_entries: Vec<p904_sales_data::repository::Model>,
+    order_events: Vec<p915_mp_order_events::repository::Model>,
+}
 
+/// Подготовка проведения без записи: документ с заполненными ссылками и строки проекций.
+pub async fn prepare_posting(id: Uuid) -> Result<PreparedPosting> {
+    let mut document = load_prepared(id).await?;
+
+    // Установить флаг is_posted
+    document.is_posted = true;
+    document.before_write();
+
+    // Строки проекций собираем до транзакции
+    let registrator_ref = id.to_string();
+    let p900_entries =
+        p900_mp_sales_register::projection_builder::from_ym_order(&document, &registrator_ref)
+            .await?;
+    let p904_entries =
+        p904_sales_data::projection_builder::from_ym_order(&document, &registrator_ref).await?;
+    // Таймлайн событий заказа (p915): дата заказа / дата доставки.
+    let order_events = p915_mp_order_events::builder::from_ym_order(&document, &registrator_ref);
+
+    Ok(PreparedPosting {
+        id,
+        document,
+        p900_entries,
+        p904_entries,
+        order_events,
+    })
+}
+
+/// Запись проведения в транзакцию `uow`: документ (включая строки в items table),
+/// p900, p904, p915 и журнал «История».
+pub async fn write_posting(uow: &mut UnitOfWork, prepared: &PreparedPosting) -> Result<()> {
+    let registrator_ref = prepared.id.to_string();
+    repository::upsert_document_with_conn(uow.conn(), &prepared.document).await?;
+    p900_mp_sales_register::repository::delete_by_registrator_with_conn(
+        uow.conn(),
+        &registrator_ref,
+    )
+    .await?;
+    p904_sales_data::repository::delete_by_registrator_with_conn(uow.conn(), &registrator_ref)
+        .await?;
+    p915_mp_order_events::repository::delete_by_registrator_with_conn(
+        uow.conn(),
+        "a013_ym_order",
+        &registrator_ref,
+    )
+    .await?;
+    for entry in &prepared.p900_entries {
+        p900_mp_sales_register::repository::upsert_entry_with_conn(uow.conn(), entry).await?;
+    }
+    for entry in &prepared.p904_entries {
+        p904_sales_data::repository::upsert_entry_with_conn(uow.conn(), entry).await?;
+    }
+    for event in &prepared.order_events {
+        p915_mp_order_events::repository::insert_entry_raw_with_conn(uow.conn(), event).await?;
+    }
+    uow.record_audit(
+        "a013_ym_order",
+        &registrator_ref,
+        DocumentAuditAction::Posted,
+    )
+    .await?;
+    // Сигнал клиентам обновить открытые списки a013.
+    uow.after_commit(|| super::change_token::TOKEN.bump());
     Ok(())
 }
 
@@ -98,22 +143,40 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
     document.is_posted = false;
     document.before_write();
 
-    // Сохранить документ
-    repository::upsert_document(&document).await?;
-
-    // Удалить проекции
-    crate::projections::p900_mp_sales_register::service::delete_by_registrator(&id.to_string())
+    let registrator_ref = id.to_string();
+    let mut uow = UnitOfWork::begin().await?;
+    repository::upsert_document_with_conn(uow.conn(), &document).await?;
+    p900_mp_sales_register::repository::delete_by_registrator_with_conn(
+        uow.conn(),
+        &registrator_ref,
+    )
+    .await?;
+    p904_sales_data::repository::delete_by_registrator_with_conn(uow.conn(), &registrator_ref)
         .await?;
-    crate::projections::p904_sales_data::repository::delete_by_registrator(&id.to_string()).await?;
-    crate::projections::p915_mp_order_events::repository::delete_by_registrator_ref(
+    p915_mp_order_events::repository::delete_by_registrator_with_conn(
+        uow.conn(),
+        "a013_ym_order",
+        &registrator_ref,
+    )
+    .await?;
+    // Сигнал клиентам обновить открытые списки a013.
+    uow.after_commit(|| super::change_token::TOKEN.bump());
+    uow.record_audit(
+        "a013_ym_order",
         &id.to_string(),
+        DocumentAuditAction::Unposted,
     )
     .await?;
+    uow.commit().await?;
 
     tracing::info!("Unposted document a013: {}", id);
 
-    // Сигнал клиентам обновить открытые списки a013.
-    super::change_token::TOKEN.bump();
-
     Ok(())
 }
+
+/// Строки p904 заказа с той же подготовкой, что при проведении, но без записи
+/// документа и проекций — для массовой пересборки p904.
+pub async fn build_p904_rows(id: Uuid) -> Result<Vec<p904_sales_data::repository::Model>> {
+    let document = load_prepared(id).await?;
+    p904_sales_data::projection_builder::from_ym_order(&document, &id.to_string()).await
+}
@@ -5,7 +5,9 @@ use contracts::domain::a013_ym_order::aggregate::{
 };
 use contracts::domain::common::{BaseAggregate, EntityMetadata};
 use sea_orm::entity::prelude::*;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -26,6 +28,7 @@ pub struct Model {
     pub source_meta_json: String,
     pub is_deleted: bool,
     pub is_posted: bool,
+    pub is_sandbox: bool,
     pub is_error: bool,
     // Денормализованные поля для быстрых запросов списка
     #[sea_orm(nullable)]
@@ -186,6 +189,7 @@ impl From<Model> for YmOrder {
             state,
             source_meta,
             is_posted: m.is_posted,
+            is_sandbox: m.is_sandbox,
             is_error: m.is_error,
         }
     }
@@ -304,8 +308,19 @@ struct DenormalizedFields {
 }
 
 pub async fn upsert_document(aggregate: &YmOrder) -> Result<Uuid> {
+    upsert_document_with_conn(conn(), aggregate).await
+}
+
+pub async fn upsert_document_with_conn<C: ConnectionTrait>(
+    db: &C,
+    aggregate: &YmOrder,
+) -> Result<Uuid> {
     let uuid = aggregate.base.id.value();
-    let existing = get_by_document_no(&aggregate.header.document_no).await?;
+    let existing: Option<YmOrder> = Entity::find()
+        .filter(Column::DocumentNo.eq(&aggregate.header.document_no))
+        .one(db)
+        .await?
+        .map(Into::into);
 
     let header_json = serde_json::to_string(&aggregate.header)?;
     let lines_json = serde_json::to_string(&aggregate.lines)?;
@@ -329,6 +344,7 @@ pub async fn upsert_document(aggregate: &YmOrder) -> Result<Uuid> {
             source_meta_json: Set(source_meta_json),
             is_deleted: Set(aggregate.base.metadata.is_deleted),
             is_posted: Set(aggregate.is_posted),
+            is_sandbox: Set(aggregate.is_sandbox),
             is_error: Set(aggregate.is_error),
             // Денормализованные поля
             status_changed_at: Set(denorm.status_changed_at),
@@ -351,7 +367,7 @@ pub async fn upsert_document(aggregate: &YmOrder) -> Result<Uuid> {
             version: Set(aggregate.base.metadata.version + 1),
             created_at: sea_orm::ActiveValue::NotSet,
         };
-        active.update(conn()).await?;
+        active.update(db).await?;
         existing_uuid
     } else {
         let active = ActiveModel {
@@ -366,6 +382,7 @@ pub async fn upsert_document(aggregate: &YmOrder) -> Result<Uuid> {
             source_meta_json: Set(source_meta_json),
             is_deleted: Set(aggregate.base.metadata.is_deleted),
             is_posted: Set(aggregate.is_posted),
+            is_sandbox: Set(aggregate.is_sandbox),
             is_error: Set(aggregate.is_error),
             // Денормализованные поля
             status_changed_at: Set(denorm.status_changed_at),
@@ -388,12 +405,12 @@ pub async fn upsert_document(aggregate: &YmOrder) -> Result<Uuid> {
             updated_at: Set(Some(aggregate.base.metadata.updated_at)),
             version: Set(aggregate.base.metadata.version),
         };
-        active.insert(conn()).await?;
+        active.insert(db).await?;
         uuid
     };
 
     // Сохраняем табличную часть (items)
-    save_items(&result_uuid.to_string(), &aggregate.lines).await?;
+    save_items_with_conn(db, &result_uuid.to_string(), &aggregate.lines).await?;
 
     Ok(result_uuid)
 }
@@ -416,8 +433,16 @@ pub async fn soft_delete(id: Uuid) -> Result<bool> {
 /// Сохранение строк документа в табличную часть
 /// Удаляет существующие строки и вставляет новые
 pub async fn save_items(order_id: &str, lines: &[YmOrderLine]) -> Result<()> {
+    save_items_with_conn(conn(), order_id, lines).await
+}
+
+pub async fn save_items_with_conn<C: ConnectionTrait>(
+    db: &C,
+    order_id: &str,
+    lines: &[YmOrderLine],
+) -> Result<()> {
     // Удаляем существующие строки
-    delete_items(order_id).await?;
+    delete_items_with_conn(db, order_id).await?;
 
     // Вставляем новые строки
     for line in lines {
@@ -443,7 +468,7 @@ pub async fn save_items(order_id: &str, lines: &[YmOrderLine]) -> Result<()> {
             status: Set(line.status.clone()),
             dealer_price_ut: Set(line.dealer_price_ut),
         };
-        items::Entity::insert(active).exec(conn()).await?;
+        items::Entity::insert(active).exec(db).await?;
     }
 
     Ok(())
@@ -460,10 +485,10 @@ pub async fn get_items(order_id: &str) -> Result<Vec<YmOrderLine>> {
 }
 
 /// Удаление строк документа из табличной части
-pub async fn delete_items(order_id: &str) -> Result<()> {
+pub async fn delete_items_with_conn<C: ConnectionTrait>(db: &C, order_id: &str) -> Result<()> {
     items::Entity::delete_many()
         .filter(items::Column::OrderId.eq(order_id))
-        .exec(conn())
+        .exec(db)
         .await?;
     Ok(())
 }
@@ -3,3 +3,4 @@ pub mod repository;
 pub mod representation;
 pub mod service;
 pub mod service_enrichment;
+pub mod sku_linking;
@@ -1,9 +1,17 @@
 use super::repository;
 use anyhow::Result;
+use contracts::domain::a014_ozon_transactions::aggregate::OzonTransactions;
+use contracts::system::document_audit::DocumentAuditAction;
 use uuid::Uuid;
 
-/// Провести документ (установить is_posted = true и создать проекции P904)
-pub async fn post_document(id: Uuid) -> Result<()> {
+use crate::projections::{p904_sales_data, p919_daily_sales_summary};
+use crate::shared::data::unit_of_work::UnitOfWork;
+use crate::shared::optimistic_lock;
+
+const TABLE: &str = "a014_ozon_transactions";
+
+/// Загрузить документ и связать с постингом A010/A011 (без записи в БД).
+async fn load_prepared(id: Uuid) -> Result<OzonTransactions> {
     // Загрузить документ
     let mut document = repository::get_by_id(id)
         .await?
@@ -43,18 +51,55 @@ pub async fn post_document(id: Uuid) -> Result<()> {
     // Обогатить items данными из постинга
     super::service_enrichment::enrich_items_from_posting(&mut document).await?;
 
+    Ok(document)
+}
+
+/// Провести документ (установить is_posted = true и создать проекции P904)
+pub async fn post_document(id: Uuid) -> Result<()> {
+    post_document_at_version(id, None).await
+}
+
+/// Проведение из карточки: `expected_version` — версия, которую видит пользователь
+/// (см. `shared::optimistic_lock`); `None` — без проверки.
+pub async fn post_document_at_version(id: Uuid, expected_version: Option<i32>) -> Result<()> {
+    let mut document = load_prepared(id).await?;
+
     // Установить флаг is_posted
     document.is_posted = true;
     document.before_write();
 
-    // Сохранить документ
-    repository::upsert_by_operation_id(&document).await?;
-
-    // Удалить старые проекции (если были)
-    crate::projections::p904_sales_data::repository::delete_by_registrator(&id.to_string()).await?;
-
-    // Создать новые проекции в P904
-    crate::projections::p904_sales_data::service::project_ozon_transactions(&document, id).await?;
+    // Строки P904 собираем до транзакции
+    let registrator_ref = id.to_string();
+    let p904_entries =
+        p904_sales_data::projection_builder::from_ozon_transactions(&document, &registrator_ref)
+            .await?;
+    let p919_contribution = p919_daily_sales_summary::builder::from_ozon_transactions(
+        &document,
+        &registrator_ref,
+        &p904_entries,
+    );
+
+    // Документ и замена проекций — одна транзакция
+    let uow = UnitOfWork::begin().await?;
+    if let Some(expected) = expected_version {
+        optimistic_lock::claim_version(uow.conn(), TABLE, &registrator_ref, expected).await?;
+        document.base.metadata.version = expected;
+    }
+    repository::upsert_by_operation_id_with_conn(uow.conn(), &document).await?;
+    p904_sales_data::repository::delete_by_registrator_with_conn(uow.conn(), &registrator_ref)
+        .await?;
+    for entry in &p904_entries {
+        p904_sales_data::repository::upsert_entry_with_conn(uow.conn(), entry).await?;
+    }
+    p919_daily_sales_summary::service::replace_for_registrator_with_conn(
+        uow.conn(),
+        &registrator_ref,
+        p919_contribution.as_ref(),
+    )
+    .await?;
+    uow.record_audit(TABLE, &id.to_string(), DocumentAuditAction::Posted)
+        .await?;
+    uow.commit().await?;
 
     tracing::info!("Posted document a014: {} - P904 projections created", id);
 
@@ -63,6 +108,11 @@ pub async fn post_document(id: Uuid) -> Result<()> {
 
 /// Отменить проведение документа (установить is_posted = false и удалить проекции)
 pub async fn unpost_document(id: Uuid) -> Result<()> {
+    unpost_document_at_version(id, None).await
+}
+
+/// Отмена проведения из карточки, `expected_version` — как в [`post_document_at_version`].
+pub async fn unpost_document_at_version(id: Uuid, expected_version: Option<i32>) -> Result<()> {
     // Загрузить документ
     let mut document = repository::get_by_id(id)
         .await?
@@ -74,12 +124,31 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
     document.posting_ref_type = None;
     document.before_write();
 
-    // Сохранить документ
-    repository::upsert_by_operation_id(&document).await?;
-
-    // Удалить проекции
-    crate::projections::p904_sales_data::repository::delete_by_registrator(&id.to_string()).await?;
+    let registrator_ref = id.to_string();
+    let uow = UnitOfWork::begin().await?;
+    if let Some(expected) = expected_version {
+        optimistic_lock::claim_version(uow.conn(), TABLE, &registrator_ref, expected).await?;
+        document.base.metadata.version = expected;
+    }
+    repository::upsert_by_operation_id_with_conn(uow.conn(), &document).await?;
+    p904_sales_data::repository::delete_by_registrator_with_conn(uow.conn(), &registrator_ref)
+        .await?;
+    p919_daily_sales_summary::service::replace_for_registrator_with_conn(
+        uow.conn(),
+        &registrator_ref,
+        None,
+    )
+    .await?;
+    uow.record_audit(TABLE, &id.to_string(), DocumentAuditAction::Unposted)
+        .await?;
+    uow.commit().await?;
 
     tracing::info!("Unposted document a014: {}", id);
     Ok(())
 }
+
+/// Строки p904 транзакции без записи документа и проекций (массовая пересборка p904).
+pub async fn build_p904_rows(id: Uuid) -> Result<Vec<p904_sales_data::repository::Model>> {
+    let document = load_prepared(id).await?;
+    p904_sales_data::projection_builder::from_ozon_transactions(&document, &id.to_string()).await
+}
@@ -6,7 +6,7 @@ use contracts::domain::a014_ozon_transactions::aggregate::{
 };
 use contracts::domain::common::{BaseAggregate, EntityMetadata};
 use sea_orm::entity::prelude::*;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set};
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, Set};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -145,6 +145,7 @@ pub async fn list_with_filters(
     transaction_type: Option<String>,
     operation_type_name: Option<String>,
     posting_number: Option<String>,
+    organization_id: Option<String>,
 ) -> Result<Vec<OzonTransactions>> {
     // Получаем все неудаленные транзакции
     let mut query = Entity::find().filter(Column::IsDeleted.eq(false));
@@ -196,6 +197,14 @@ pub async fn list_with_filters(
                 }
             }
 
+            // Фильтр по организации: ссылки сравниваются без кавычек и регистра
+            if let Some(ref org) = organization_id {
+                let normalize = |s: &str| s.replace('"', "").trim().to_lowercase();
+                if normalize(&txn.header.organization_id) != normalize(org) {
+                    return false;
+                }
+            }
+
             true
         })
         .collect();
@@ -240,10 +249,21 @@ pub async fn get_by_posting_number(posting_number: &str) -> Result<Vec<OzonTrans
 
 /// Идемпотентная вставка/обновление по operation_id
 pub async fn upsert_by_operation_id(aggregate: &OzonTransactions) -> Result<Uuid> {
+    upsert_by_operation_id_with_conn(conn(), aggregate).await
+}
+
+pub async fn upsert_by_operation_id_with_conn<C: ConnectionTrait>(
+    db: &C,
+    aggregate: &OzonTransactions,
+) -> Result<Uuid> {
     let uuid = aggregate.base.id.value();
 
     // Проверяем, существует ли транзакция с таким operation_id
-    let existing = get_by_operation_id(aggregate.header.operation_id).await?;
+    let existing: Option<OzonTransactions> = Entity::find()
+        .filter(Column::OperationId.eq(aggregate.header.operation_id))
+        .one(db)
+        .await?
+        .map(Into::into);
 
     let header_json = serde_json::to_string(&aggregate.header)?;
     let posting_json = serde_json::to_string(&aggregate.posting)?;
@@ -274,7 +294,7 @@ pub async fn upsert_by_operation_id(aggregate: &OzonTransactions) -> Result<Uuid
             version: Set(aggregate.base.metadata.version + 1),
             created_at: sea_orm::ActiveValue::NotSet,
         };
-        active.update(conn()).await?;
+        active.update(db).await?;
         Ok(existing_uuid)
     } else {
         // Вставляем новую транзакцию
@@ -298,7 +318,7 @@ pub async fn upsert_by_operation_id(aggregate: &OzonTransactions) -> Result<Uuid
             updated_at: Set(Some(aggregate.base.metadata.updated_at)),
             version: Set(aggregate.base.metadata.version),
         };
-        active.insert(conn()).await?;
+        active.insert(db).await?;
         Ok(uuid)
     }
 }
@@ -222,6 +222,7 @@ pub async fn list_with_filters_as_dto(
     transaction_type: Option<String>,
     operation_type_name: Option<String>,
     posting_number: Option<String>,
+    organization_id: Option<String>,
 ) -> anyhow::Result<Vec<OzonTransactionsListDto>> {
     let aggregates = repository::list_with_filters(
         date_from,
@@ -229,6 +230,7 @@ pub async fn list_with_filters_as_dto(
         transaction_type,
         operation_type_name,
         posting_number,
+        organization_id,
     )
     .await?;
 
@@ -93,24 +93,39 @@ pub async fn enrich_items_from_posting(transaction: &mut OzonTransactions) -> an
                 total_price += price;
             }
 
-            // Устанавливаем marketplace_product_ref как артикул (offer_id)
-            enriched.marketplace_product_ref = Some(line_data.offer_id.clone());
-
-            // Используем find_or_create_for_sale для получения a007 и nomenclature
+            // Сначала существующий a007 по SKU или артикулу (offer_id), затем
+            // find_or_create_for_sale для получения a007 и nomenclature
             let marketplace_sku = item.sku.to_string();
-            match crate::domain::a007_marketplace_product::service::find_or_create_for_sale(
-                crate::domain::a007_marketplace_product::service::FindOrCreateParams {
-                    marketplace_ref: marketplace_ref.clone(),
-                    connection_mp_ref: connection_mp_ref.clone(),
-                    marketplace_sku: marketplace_sku.clone(),
-                    article: None,
-                    barcode: line_data.barcode.clone(),
-                    title: line_data.name.clone(),
-                },
-            )
-            .await
-            {
+            let existing =
+                crate::domain::a007_marketplace_product::service::resolve_marketplace_product_ref(
+                    &connection_mp_ref,
+                    &marketplace_sku,
+                    Some(&line_data.offer_id),
+                )
+                .await
+                .ok()
+                .flatten()
+                .and_then(|r| Uuid::parse_str(&r).ok());
+            let resolved = match existing {
+                Some(mp_uuid) => Ok(mp_uuid),
+                None => {
+                    crate::domain::a007_marketplace_product::service::find_or_create_for_sale(
+                        crate::domain::a007_marketplace_product::service::FindOrCreateParams {
+                            marketplace_ref: marketplace_ref.clone(),
+                            connection_mp_ref: connection_mp_ref.clone(),
+                            marketplace_sku: marketplace_sku.clone(),
+                            article: None,
+                            barcode: line_data.barcode.clone(),
+                            title: line_data.name.clone(),
+                        },
+                    )
+                    .await
+                }
+            };
+            match resolved {
                 Ok(mp_uuid) => {
+                    // marketplace_product_ref — ссылка на a007
+                    enriched.marketplace_product_ref = Some(mp_uuid.to_string());
                     // Получаем nomenclature_ref из найденного a007
                     if let Ok(Some(product)) =
                         crate::domain::a007_marketplace_product::service::get_by_id(mp_uuid).await
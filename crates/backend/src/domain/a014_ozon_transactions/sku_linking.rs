@@ -0,0 +1,164 @@
+//! Привязка строк транзакции к товарам маркетплейса (a007) по SKU Ozon.
+//!
+//! `items[].sku` — SKU Ozon, а карточки a007 из импорта товаров ключуются по
+//! `product_id`, поэтому прямое совпадение `marketplace_sku` есть не всегда. Порядок:
+//! 1. a007 с `(connection_mp_ref, marketplace_sku = sku)`;
+//! 2. строка постинга (a010/a011) с этим SKU → `offer_id` → единственный a007 с таким
+//!    артикулом в подключении.
+//!
+//! Карточки не создаются: ненайденные SKU попадают в отчёт u509 для ручного разбора.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use contracts::domain::a014_ozon_transactions::aggregate::{
+    OzonTransactions, OzonTransactionsItem,
+};
+use uuid::Uuid;
+
+use crate::domain::a007_marketplace_product;
+
+/// Итог привязки строк одного документа.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LinkOutcome {
+    /// Строк, у которых появилась или сменилась ссылка
+    pub linked: usize,
+    /// SKU строк, для которых товар не найден
+    pub unmatched: Vec<i64>,
+}
+
+/// Ссылка строки указывает на a007 (uuid). Ранние проведения записывали сюда
+/// `offer_id` — такие строки считаются непривязанными.
+pub fn has_product_ref(item: &OzonTransactionsItem) -> bool {
+    item.marketplace_product_ref
+        .as_deref()
+        .is_some_and(|r| Uuid::parse_str(r.trim()).is_ok())
+}
+
+/// Строки, которые нужно (пере)привязать.
+fn needs_link(item: &OzonTransactionsItem, relink: bool) -> bool {
+    item.sku != 0 && (relink || !has_product_ref(item))
+}
+
+/// `sku → offer_id` из строк постинга FBS или FBO.
+async fn posting_offer_ids(posting_number: &str) -> Result<HashMap<i64, String>> {
+    if posting_number.trim().is_empty() {
+        return Ok(HashMap::new());
+    }
+    let lines: Vec<(String, String)> =
+        match crate::domain::a010_ozon_fbs_posting::service::get_by_document_no(posting_number)
+            .await?
+        {
+            Some(posting) => posting
+                .lines
+                .into_iter()
+                .map(|l| (l.product_id, l.offer_id))
+                .collect(),
+            None => {
+                crate::domain::a011_ozon_fbo_posting::service::get_by_document_no(posting_number)
+                    .await?
+                    .map(|posting| {
+                        posting
+                            .lines
+                            .into_iter()
+                            .map(|l| (l.product_id, l.offer_id))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+        };
+    Ok(lines
+        .into_iter()
+        .filter_map(|(product_id, offer_id)| {
+            product_id
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .map(|sku| (sku, offer_id))
+        })
+        .collect())
+}
+
+/// Проставляет `marketplace_product_ref` строкам документа. `relink` — перепроверить
+/// и уже привязанные строки (ссылка меняется, только если товар найден).
+pub async fn link_items(document: &mut OzonTransactions, relink: bool) -> Result<LinkOutcome> {
+    let mut outcome = LinkOutcome::default();
+    if !document.items.iter().any(|item| needs_link(item, relink)) {
+        return Ok(outcome);
+    }
+    let connection_mp_ref = document.header.connection_id.clone();
+    // Постинг читается, только если SKU не нашёлся напрямую
+    let mut offer_ids: Option<HashMap<i64, String>> = None;
+
+    for item in document.items.iter_mut() {
+        if !needs_link(item, relink) {
+            continue;
+        }
+        let sku = item.sku.to_string();
+        let mut resolved = a007_marketplace_product::service::resolve_marketplace_product_ref(
+            &connection_mp_ref,
+            &sku,
+            None,
+        )
+        .await?;
+        if resolved.is_none() {
+            if offer_ids.is_none() {
+                offer_ids = Some(posting_offer_ids(&document.posting.posting_number).await?);
+            }
+            if let Some(offer_id) = offer_ids.as_ref().and_then(|m| m.get(&item.sku)) {
+                resolved = a007_marketplace_product::service::resolve_marketplace_product_ref(
+                    &connection_mp_ref,
+                    "",
+                    Some(offer_id),
+                )
+                .await?;
+            }
+        }
+
+        match resolved {
+            Some(product_ref) => {
+                if item.marketplace_product_ref.as_deref() != Some(product_ref.as_str()) {
+                    item.marketplace_product_ref = Some(product_ref);
+                    outcome.linked += 1;
+                }
+            }
+            None => outcome.unmatched.push(item.sku),
+        }
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(sku: i64, product_ref: Option<&str>) -> OzonTransactionsItem {
+        OzonTransactionsItem {
+            name: "Товар".to_string(),
+            sku,
+            price: None,
+            ratio: None,
+            marketplace_product_ref: product_ref.map(str::to_string),
+            nomenclature_ref: None,
+        }
+    }
+
+    #[test]
+    fn offer_id_is_not_a_product_ref() {
+        assert!(!has_product_ref(&item(1, None)));
+        assert!(!has_product_ref(&item(1, Some("ART-001"))));
+        assert!(has_product_ref(&item(
+            1,
+            Some("5b0a1c7e-3f1d-4c2e-9a41-0f6f1d2c3b4a")
+        )));
+    }
+
+    #[test]
+    fn only_unlinked_items_with_sku_need_linking() {
+        let linked = item(1, Some("5b0a1c7e-3f1d-4c2e-9a41-0f6f1d2c3b4a"));
+        assert!(!needs_link(&linked, false));
+        assert!(needs_link(&linked, true));
+        assert!(needs_link(&item(2, Some("ART-002")), false));
+        assert!(!needs_link(&item(0, None), true));
+    }
+}
@@ -1,8 +1,13 @@
 use super::repository;
 use anyhow::Result;
 use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::system::document_audit::DocumentAuditAction;
 use uuid::Uuid;
 
+use crate::shared::data::unit_of_work::UnitOfWork;
+use crate::shared::optimistic_lock;
+use crate::system::cdc::service::ChangeOp;
+
 /// Загрузить подключение маркетплейса для документа. Используется единожды в начале
 /// `post_document` и передаётся во все под-функции, которые в нём нуждаются.
 async fn load_connection(
@@ -82,6 +87,12 @@ fn registrator_ref(id: Uuid) -> String {
 }
 
 pub async fn post_document(id: Uuid) -> Result<()> {
+    post_document_at_version(id, None).await
+}
+
+/// Проведение из карточки: `expected_version` — версия, которую видит пользователь
+/// (см. `shared::optimistic_lock`); `None` — без проверки.
+pub async fn post_document_at_version(id: Uuid, expected_version: Option<i32>) -> Result<()> {
     let mut document = repository::get_by_id(id)
         .await?
         .ok_or_else(|| anyhow::anyhow!("Document not found: {}", id))?;
@@ -110,53 +121,80 @@ pub async fn post_document(id: Uuid) -> Result<()> {
     document.base.metadata.is_posted = true;
     document.before_write();
 
+    let reg_ref = id.to_string();
+    let p909_ref = registrator_ref(id);
+    // Стадия 2 воронки p916: движения «заказ»/«отмена».
+    let p916_rows = crate::projections::p916_mp_sales_funnel_turnovers::builder::from_wb_orders(
+        &document, &reg_ref,
+    );
+
+    // Документ, p909, GL, p916 и событие CDC — одна транзакция: ошибка на любом шаге
+    // не оставляет проведённый документ без движений.
+    let mut uow = UnitOfWork::begin().await?;
+    if let Some(expected) = expected_version {
+        optimistic_lock::claim_version(uow.conn(), REGISTRATOR_TYPE, &reg_ref, expected).await?;
+        document.base.metadata.version = expected;
+    }
+
     // Direct UPDATE by ID — skips the get_by_document_no round-trip of upsert_document.
-    repository::update_posted_document(&document).await?;
+    repository::update_posted_document_with_conn(uow.conn(), &document).await?;
 
     // Идемпотентное перепроведение: удаляем старые строки p909 и GL, затем
     // создаём заново, чтобы повторный Post всегда давал актуальный результат.
-    let p909_ref = registrator_ref(id);
-    crate::projections::p909_mp_order_line_turnovers::service::remove_by_registrator_ref(&p909_ref)
-        .await?;
-    crate::general_ledger::service::remove_by_registrator(REGISTRATOR_TYPE, &id.to_string())
-        .await?;
+    crate::projections::p909_mp_order_line_turnovers::service::remove_by_registrator_ref_with_conn(
+        uow.conn(),
+        &p909_ref,
+    )
+    .await?;
+    crate::general_ledger::repository::delete_by_registrator_with_conn(
+        uow.conn(),
+        REGISTRATOR_TYPE,
+        &reg_ref,
+    )
+    .await?;
 
-    crate::projections::p909_mp_order_line_turnovers::service::project_wb_order(&document, id)
-        .await?;
+    crate::projections::p909_mp_order_line_turnovers::service::project_wb_order_with_conn(
+        uow.conn(),
+        &document,
+        id,
+    )
+    .await?;
 
-    // Стадия 2 воронки p916: движения «заказ»/«отмена». delete-by-registrator + insert
-    // атомарны в собственной транзакции. Проведение a015 в целом не обёрнуто в единую
-    // транзакцию (p909/GL идут отдельными autocommit-вызовами) — это существующее свойство
-    // модуля, не специфика p916; общий рефактор проведения — вне рамок этой задачи.
-    {
-        use sea_orm::TransactionTrait;
-        let reg_ref = id.to_string();
-        let db = crate::shared::data::db::get_connection();
-        let txn = db.begin().await?;
-        crate::projections::p916_mp_sales_funnel_turnovers::repository::delete_by_registrator_with_conn(
-            &txn,
-            crate::projections::p916_mp_sales_funnel_turnovers::builder::REG_A015,
-            &reg_ref,
-        )
-        .await?;
-        let rows = crate::projections::p916_mp_sales_funnel_turnovers::builder::from_wb_orders(
-            &document, &reg_ref,
-        );
-        crate::projections::p916_mp_sales_funnel_turnovers::repository::insert_many_with_conn(
-            &txn, &rows,
-        )
+    crate::projections::p916_mp_sales_funnel_turnovers::repository::delete_by_registrator_with_conn(
+        uow.conn(),
+        crate::projections::p916_mp_sales_funnel_turnovers::builder::REG_A015,
+        &reg_ref,
+    )
+    .await?;
+    crate::projections::p916_mp_sales_funnel_turnovers::repository::insert_many_with_conn(
+        uow.conn(),
+        &p916_rows,
+    )
+    .await?;
+
+    uow.record_changes(&[repository::change_event(id, ChangeOp::Posted)])
         .await?;
-        txn.commit().await?;
-    }
 
     // Сигнал клиентам обновить открытые списки a015 (margin_pro и пр. могли измениться).
-    super::change_token::TOKEN.bump();
+    uow.after_commit(|| super::change_token::TOKEN.bump());
+    uow.record_audit(
+        "a015_wb_orders",
+        &id.to_string(),
+        DocumentAuditAction::Posted,
+    )
+    .await?;
+    uow.commit().await?;
 
     tracing::info!("Posted WB Orders document: {}", id);
     Ok(())
 }
 
 pub async fn unpost_document(id: Uuid) -> Result<()> {
+    unpost_document_at_version(id, None).await
+}
+
+/// Отмена проведения из карточки, `expected_version` — как в [`post_document_at_version`].
+pub async fn unpost_document_at_version(id: Uuid, expected_version: Option<i32>) -> Result<()> {
     let mut document = repository::get_by_id(id)
         .await?
         .ok_or_else(|| anyhow::anyhow!("Document not found: {}", id))?;
@@ -165,21 +203,48 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
     document.base.metadata.is_posted = false;
     document.before_write();
 
-    repository::update_posted_document(&document).await?;
+    let reg_ref = id.to_string();
+    let p909_ref = registrator_ref(id);
+
+    let mut uow = UnitOfWork::begin().await?;
+    if let Some(expected) = expected_version {
+        optimistic_lock::claim_version(uow.conn(), REGISTRATOR_TYPE, &reg_ref, expected).await?;
+        document.base.metadata.version = expected;
+    }
+
+    repository::update_posted_document_with_conn(uow.conn(), &document).await?;
 
     // Убираем все связанные результаты при отмене проведения.
-    let p909_ref = registrator_ref(id);
-    crate::projections::p909_mp_order_line_turnovers::service::remove_by_registrator_ref(&p909_ref)
-        .await?;
-    crate::general_ledger::service::remove_by_registrator(REGISTRATOR_TYPE, &id.to_string())
-        .await?;
-    crate::projections::p916_mp_sales_funnel_turnovers::repository::delete_by_registrator_ref(
-        &id.to_string(),
+    crate::projections::p909_mp_order_line_turnovers::service::remove_by_registrator_ref_with_conn(
+        uow.conn(),
+        &p909_ref,
+    )
+    .await?;
+    crate::general_ledger::repository::delete_by_registrator_with_conn(
+        uow.conn(),
+        REGISTRATOR_TYPE,
+        &reg_ref,
+    )
+    .await?;
+    crate::projections::p916_mp_sales_funnel_turnovers::repository::delete_by_registrator_with_conn(
+        uow.conn(),
+        crate::projections::p916_mp_sales_funnel_turnovers::builder::REG_A015,
+        &reg_ref,
     )
     .await?;
 
+    uow.record_changes(&[repository::change_event(id, ChangeOp::Unposted)])
+        .await?;
+
     // Сигнал клиентам обновить открытые списки a015.
-    super::change_token::TOKEN.bump();
+    uow.after_commit(|| super::change_token::TOKEN.bump());
+    uow.record_audit(
+        "a015_wb_orders",
+        &id.to_string(),
+        DocumentAuditAction::Unposted,
+    )
+    .await?;
+    uow.commit().await?;
 
     tracing::info!("Unposted WB Orders document: {}", id);
 
@@ -6,11 +6,15 @@ use contracts::domain::a015_wb_orders::aggregate::{
 };
 use contracts::domain::common::{BaseAggregate, EntityMetadata};
 use sea_orm::entity::prelude::*;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect, Set};
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect, Set};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+use crate::shared::data::unit_of_work::UnitOfWork;
+use crate::shared::quick_filter::QuickFilterColumn;
+use crate::system::cdc::service::{ChangeEvent, ChangeOp};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "a015_wb_orders")]
@@ -40,6 +44,7 @@ pub struct Model {
     pub dealer_price_ut: Option<f64>,
     pub is_deleted: bool,
     pub is_posted: bool,
+    pub is_sandbox: bool,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
     pub version: i32,
@@ -116,6 +121,7 @@ impl From<Model> for WbOrders {
             geography,
             source_meta,
             is_posted: m.is_posted,
+            is_sandbox: m.is_sandbox,
             marketplace_product_ref: m.marketplace_product_ref,
             nomenclature_ref: m.nomenclature_ref,
             base_nomenclature_ref: m.base_nomenclature_ref,
@@ -181,6 +187,10 @@ pub async fn list_by_income_id(income_id: i64) -> Result<Vec<WbOrders>> {
             .try_get::<i32>("", "is_posted")
             .map(|v| v != 0)
             .unwrap_or(false);
+        let is_sandbox: bool = row
+            .try_get::<i32>("", "is_sandbox")
+            .map(|v| v != 0)
+            .unwrap_or(false);
         let version: i32 = row.try_get("", "version").unwrap_or(0);
 
         let model = Model {
@@ -207,6 +217,7 @@ pub async fn list_by_income_id(income_id: i64) -> Result<Vec<WbOrders>> {
             dealer_price_ut: None,
             is_deleted,
             is_posted,
+            is_sandbox,
             created_at: None,
             updated_at: None,
             version,
@@ -281,6 +292,10 @@ pub async fn list_for_advert_attribution(
             .try_get::<i32>("", "is_posted")
             .map(|v| v != 0)
             .unwrap_or(false);
+        let is_sandbox: bool = row
+            .try_get::<i32>("", "is_sandbox")
+            .map(|v| v != 0)
+            .unwrap_or(false);
         let version: i32 = row.try_get("", "version").unwrap_or(0);
 
         let model = Model {
@@ -307,6 +322,7 @@ pub async fn list_for_advert_attribution(
             dealer_price_ut: None,
             is_deleted,
             is_posted,
+            is_sandbox,
             created_at: None,
             updated_at: None,
             version,
@@ -376,6 +392,10 @@ pub async fn list_by_numeric_order_ids(order_ids: &[i64]) -> Result<Vec<WbOrders
             .try_get::<i32>("", "is_posted")
             .map(|v| v != 0)
             .unwrap_or(false);
+        let is_sandbox: bool = row
+            .try_get::<i32>("", "is_sandbox")
+            .map(|v| v != 0)
+            .unwrap_or(false);
         let version: i32 = row.try_get("", "version").unwrap_or(0);
 
         let model = Model {
@@ -402,6 +422,7 @@ pub async fn list_by_numeric_order_ids(order_ids: &[i64]) -> Result<Vec<WbOrders
             dealer_price_ut: None,
             is_deleted,
             is_posted,
+            is_sandbox,
             created_at: None,
             updated_at: None,
             version,
@@ -615,6 +636,7 @@ pub async fn list_by_date_range(
     Ok(models.into_iter().map(|m| m.into()).collect())
 }
 
+/// Id документов за период для перепроведения, без документов песочницы.
 pub async fn list_ids_by_date_range(
     date_from: &str,
     date_to: &str,
@@ -626,6 +648,7 @@ pub async fn list_ids_by_date_range(
         .select_only()
         .column(Column::Id)
         .filter(Column::IsDeleted.eq(false))
+        .filter(Column::IsSandbox.eq(false))
         .filter(Column::DocumentDate.gte(date_from))
         .filter(Column::DocumentDate.lte(to_str));
 
@@ -694,8 +717,10 @@ struct IdOnly {
 
 /// Direct UPDATE by ID for the posting flow — skips the `get_by_document_no` round-trip of
 /// `upsert_document`. Use only when the document was loaded by ID and is guaranteed to exist.
-pub async fn update_posted_document(document: &WbOrders) -> Result<()> {
-    let db = get_connection();
+pub async fn update_posted_document_with_conn<C: ConnectionTrait>(
+    db: &C,
+    document: &WbOrders,
+) -> Result<()> {
     let uuid = document.base.id.value().to_string();
 
     let header_json = serde_json::to_string(&document.header)?;
@@ -728,6 +753,7 @@ pub async fn update_posted_document(document: &WbOrders) -> Result<()> {
         dealer_price_ut: Set(document.line.dealer_price_ut),
         is_deleted: Set(document.base.metadata.is_deleted),
         is_posted: Set(document.is_posted),
+        is_sandbox: Set(document.is_sandbox),
         updated_at: Set(Some(Utc::now())),
         version: Set(document.base.metadata.version + 1),
         created_at: sea_orm::ActiveValue::NotSet,
@@ -737,8 +763,8 @@ pub async fn update_posted_document(document: &WbOrders) -> Result<()> {
     Ok(())
 }
 
+/// Запись документа и событие ленты CDC — одной транзакцией.
 pub async fn upsert_document(document: &WbOrders) -> Result<Uuid> {
-    let db = get_connection();
     let uuid = document.base.id.value();
 
     let header_json = serde_json::to_string(&document.header)?;
@@ -784,12 +810,16 @@ pub async fn upsert_document(document: &WbOrders) -> Result<Uuid> {
             dealer_price_ut: Set(document.line.dealer_price_ut),
             is_deleted: Set(document.base.metadata.is_deleted),
             is_posted: Set(document.is_posted),
+            is_sandbox: Set(document.is_sandbox),
             updated_at: Set(Some(Utc::now())),
             version: Set(document.base.metadata.version + 1),
             created_at: sea_orm::ActiveValue::NotSet,
         };
 
-        Entity::update(active_model).exec(db).await?;
+        let uow = UnitOfWork::begin().await?;
+        Entity::update(active_model).exec(uow.conn()).await?;
+        uow.record_changes(&[change_event(existing_uuid, ChangeOp::Updated)]).await?;
+        uow.commit().await?;
         Ok(existing_uuid)
     } else {
         // INSERT - используем новый UUID
@@ -816,16 +846,25 @@ pub async fn upsert_document(document: &WbOrders) -> Result<Uuid> {
             dealer_price_ut: Set(document.line.dealer_price_ut),
             is_deleted: Set(false),
             is_posted: Set(document.is_posted),
+            is_sandbox: Set(document.is_sandbox),
             created_at: Set(Some(Utc::now())),
             updated_at: Set(Some(Utc::now())),
             version: Set(1),
         };
 
-        Entity::insert(active_model).exec(db).await?;
+        let uow = UnitOfWork::begin().await?;
+        Entity::insert(active_model).exec(uow.conn()).await?;
+        uow.record_changes(&[change_event(uuid, ChangeOp::Created)]).await?;
+        uow.commit().await?;
         Ok(uuid)
     }
 }
 
+/// Событие ленты CDC по документу a015.
+pub(crate) fn change_event(id: Uuid, op: ChangeOp) -> ChangeEvent {
+    ChangeEvent::aggregate("a015_wb_orders", id.to_string(), op)
+}
+
 pub async fn soft_delete(id: Uuid) -> Result<bool> {
     let db = get_connection();
     let id_str = id.to_string();
@@ -836,7 +875,10 @@ pub async fn soft_delete(id: Uuid) -> Result<bool> {
         let mut active_model: ActiveModel = model.into();
         active_model.is_deleted = Set(true);
         active_model.updated_at = Set(Some(Utc::now()));
-        Entity::update(active_model).exec(db).await?;
+        let uow = UnitOfWork::begin().await?;
+        Entity::update(active_model).exec(uow.conn()).await?;
+        uow.record_changes(&[change_event(id, ChangeOp::Deleted)]).await?;
+        uow.commit().await?;
         Ok(true)
     } else {
         Ok(false)
@@ -859,6 +901,45 @@ pub async fn set_posted(id: Uuid, is_posted: bool) -> Result<()> {
     Ok(())
 }
 
+/// SQL-выражения полей быстрого фильтра списка (`contracts::domain::a015_wb_orders::quick_filter`)
+pub const QUICK_FILTER_COLUMNS: &[(&str, QuickFilterColumn)] = &[
+    (
+        "status",
+        QuickFilterColumn::Cases(&[
+            ("posted", "w.is_posted = 1"),
+            ("unposted", "w.is_posted = 0"),
+        ]),
+    ),
+    (
+        "cancel",
+        QuickFilterColumn::Cases(&[
+            ("yes", "w.is_cancel = 1"),
+            ("no", "w.is_cancel IS NULL OR w.is_cancel = 0"),
+        ]),
+    ),
+    (
+        "warehouse",
+        QuickFilterColumn::Expr("json_extract(w.warehouse_json, '$.warehouse_name')"),
+    ),
+    (
+        "article",
+        QuickFilterColumn::Expr("json_extract(w.line_json, '$.supplier_article')"),
+    ),
+    (
+        "brand",
+        QuickFilterColumn::Expr("json_extract(w.line_json, '$.brand')"),
+    ),
+    (
+        "amount",
+        QuickFilterColumn::Expr("json_extract(w.line_json, '$.finished_price')"),
+    ),
+    (
+        "qty",
+        QuickFilterColumn::Expr("json_extract(w.line_json, '$.qty')"),
+    ),
+    ("date", QuickFilterColumn::Expr("w.document_date")),
+];
+
 /// Query parameters for paginated list
 #[derive(Debug, Clone)]
 pub struct WbOrdersListQuery {
@@ -866,11 +947,18 @@ pub struct WbOrdersListQuery {
     pub date_to: Option<String>,
     pub organization_id: Option<String>,
     pub search_query: Option<String>,
+    /// Условия быстрого фильтра (см. [`QUICK_FILTER_COLUMNS`])
+    pub quick_conditions: Vec<String>,
     pub sort_by: String,
     pub sort_desc: bool,
     pub limit: usize,
     pub offset: usize,
     pub show_cancelled: bool,
+    /// Только отменённые (`true`) / только неотменённые (`false`); важнее `show_cancelled`
+    pub is_cancel: Option<bool>,
+    pub is_posted: Option<bool>,
+    /// Часть названия склада (`warehouse_json.warehouse_name`)
+    pub warehouse: Option<String>,
 }
 
 /// Simplified row for list queries (no JSON deserialization)
@@ -960,9 +1048,26 @@ pub async fn list_sql(query: WbOrdersListQuery) -> Result<WbOrdersListResult> {
     }
 
     // Filter cancelled orders
-    if !query.show_cancelled {
-        conditions.push("(w.is_cancel IS NULL OR w.is_cancel = 0)".to_string());
+    match query.is_cancel {
+        Some(true) => conditions.push("w.is_cancel = 1".to_string()),
+        Some(false) => conditions.push("(w.is_cancel IS NULL OR w.is_cancel = 0)".to_string()),
+        None if !query.show_cancelled => {
+            conditions.push("(w.is_cancel IS NULL OR w.is_cancel = 0)".to_string())
+        }
+        None => {}
+    }
+    if let Some(is_posted) = query.is_posted {
+        conditions.push(format!("w.is_posted = {}", i32::from(is_posted)));
     }
+    if let Some(warehouse) = query.warehouse.as_deref().map(str::trim) {
+        if !warehouse.is_empty() {
+            conditions.push(format!(
+                "json_extract(w.warehouse_json, '$.warehouse_name') LIKE '%{}%'",
+                warehouse.replace('\'', "''")
+            ));
+        }
+    }
+    conditions.extend(query.quick_conditions.iter().cloned());
 
     let where_clause = conditions.join(" AND ");
 
@@ -981,6 +1086,9 @@ pub async fn list_sql(query: WbOrdersListQuery) -> Result<WbOrdersListResult> {
         .map(|r| r.try_get::<i32>("", "cnt").unwrap_or(0) as usize)
         .unwrap_or(0);
 
+    // Продажи ищутся и в архиве a012: старые заказы могли быть выкуплены в архивном месяце
+    let a012 = projection_archive::source("a012_wb_sales", None);
+
     // Build ORDER BY
     let order_column = match query.sort_by.as_str() {
         "document_no" => "w.document_no",
@@ -1033,7 +1141,7 @@ pub async fn list_sql(query: WbOrdersListQuery) -> Result<WbOrdersListResult> {
             w.is_posted,
             EXISTS(
                 SELECT 1
-                FROM a012_wb_sales s
+                FROM {a012} s
                 WHERE s.document_no = w.document_no
                   AND s.is_deleted = 0
                   AND s.total_price > 0
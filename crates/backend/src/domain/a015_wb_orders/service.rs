@@ -3,6 +3,7 @@ use anyhow::Result;
 use chrono::NaiveDate;
 use contracts::domain::a015_wb_orders::aggregate::WbOrders;
 use contracts::domain::common::AggregateId;
+use contracts::shared::analytics::margin::margin_pro;
 use uuid::Uuid;
 
 use crate::shared::marketplaces::wildberries::datetime::wb_business_date;
@@ -430,8 +431,13 @@ pub async fn calculate_margin_pro(document: &mut WbOrders) -> Result<()> {
     let (commission_percent, acquiring_percent) =
         load_planned_percents(&document.header.connection_id).await;
 
-    let net_price = base_price * (100.0 - commission_percent - acquiring_percent) / 100.0;
-    let margin = (net_price - dealer_price) / dealer_price * 100.0;
+    let margin = margin_pro(
+        base_price,
+        commission_percent,
+        acquiring_percent,
+        dealer_price,
+    )
+    .unwrap_or_default();
     tracing::debug!(
         "margin_pro for WB Orders {} = {:.2}% (base_price={:.2} from {})",
         document.base.id.as_string(),
@@ -466,8 +472,13 @@ pub async fn calculate_margin_pro_with_connection(
         None => load_planned_percents(&document.header.connection_id).await,
     };
 
-    let net_price = base_price * (100.0 - commission_percent - acquiring_percent) / 100.0;
-    let margin = (net_price - dealer_price) / dealer_price * 100.0;
+    let margin = margin_pro(
+        base_price,
+        commission_percent,
+        acquiring_percent,
+        dealer_price,
+    )
+    .unwrap_or_default();
     tracing::debug!(
         "margin_pro for WB Orders {} = {:.2}% (base_price={:.2} from {})",
         document.base.id.as_string(),
@@ -1,10 +1,39 @@
 use super::repository;
 use anyhow::Result;
+use contracts::domain::a016_ym_returns::aggregate::YmReturn;
+use contracts::system::document_audit::DocumentAuditAction;
 use uuid::Uuid;
 
+use crate::projections::p904_sales_data;
+use crate::shared::data::unit_of_work::UnitOfWork;
+
 /// Провести документ (установить is_posted = true и создать проекции)
 /// Возвраты YM со статусом REFUNDED формируют проекции в p904 (customer_out с минусом)
 pub async fn post_document(id: Uuid) -> Result<()> {
+    let prepared = prepare_posting(id).await?;
+
+    // Документ и замена проекций — одна транзакция
+    let mut uow = UnitOfWork::begin().await?;
+    write_posting(&mut uow, &prepared).await?;
+    uow.commit().await?;
+
+    tracing::info!(
+        "Posted document a016 (YM Return): {}, refund_status: {}",
+        id,
+        prepared.document.state.refund_status
+    );
+    Ok(())
+}
+
+/// Проведение, собранное до транзакции: документ и строки p904.
+pub struct PreparedPosting {
+    id: Uuid,
+    document: YmReturn,
+    p904_entries: Vec<p904_sales_data::repository::Model>,
+}
+
+/// Подготовка проведения без записи.
+pub async fn prepare_posting(id: Uuid) -> Result<PreparedPosting> {
     // Загрузить документ
     let mut document = repository::get_by_id(id)
         .await?
@@ -14,20 +43,33 @@ pub async fn post_document(id: Uuid) -> Result<()> {
     document.is_posted = true;
     document.before_write();
 
-    // Сохранить документ
-    repository::upsert_document(&document).await?;
+    // Строки проекций собираем до транзакции (только для REFUNDED документов)
+    let registrator_ref = id.to_string();
+    let p904_entries =
+        p904_sales_data::projection_builder::from_ym_returns(&document, &registrator_ref).await?;
 
-    // Удалить старые проекции (если были)
-    crate::projections::p904_sales_data::repository::delete_by_registrator(&id.to_string()).await?;
-
-    // Создать новые проекции (только для REFUNDED документов)
-    crate::projections::p904_sales_data::service::project_ym_returns(&document, id).await?;
-
-    tracing::info!(
-        "Posted document a016 (YM Return): {}, refund_status: {}",
+    Ok(PreparedPosting {
         id,
-        document.state.refund_status
-    );
+        document,
+        p904_entries,
+    })
+}
+
+/// Запись проведения в транзакцию `uow`: документ, p904 и журнал «История».
+pub async fn write_posting(uow: &mut UnitOfWork, prepared: &PreparedPosting) -> Result<()> {
+    let registrator_ref = prepared.id.to_string();
+    repository::upsert_document_with_conn(uow.conn(), &prepared.document).await?;
+    p904_sales_data::repository::delete_by_registrator_with_conn(uow.conn(), &registrator_ref)
+        .await?;
+    for entry in &prepared.p904_entries {
+        p904_sales_data::repository::upsert_entry_with_conn(uow.conn(), entry).await?;
+    }
+    uow.record_audit(
+        "a016_ym_returns",
+        &registrator_ref,
+        DocumentAuditAction::Posted,
+    )
+    .await?;
     Ok(())
 }
 
@@ -42,12 +84,27 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
     document.is_posted = false;
     document.before_write();
 
-    // Сохранить документ
-    repository::upsert_document(&document).await?;
-
-    // Удалить проекции
-    crate::projections::p904_sales_data::repository::delete_by_registrator(&id.to_string()).await?;
+    let registrator_ref = id.to_string();
+    let uow = UnitOfWork::begin().await?;
+    repository::upsert_document_with_conn(uow.conn(), &document).await?;
+    p904_sales_data::repository::delete_by_registrator_with_conn(uow.conn(), &registrator_ref)
+        .await?;
+    uow.record_audit(
+        "a016_ym_returns",
+        &id.to_string(),
+        DocumentAuditAction::Unposted,
+    )
+    .await?;
+    uow.commit().await?;
 
     tracing::info!("Unposted document a016 (YM Return): {}", id);
     Ok(())
 }
+
+/// Строки p904 возврата без записи в БД (массовая пересборка p904); не REFUNDED — пусто.
+pub async fn build_p904_rows(id: Uuid) -> Result<Vec<p904_sales_data::repository::Model>> {
+    let document = repository::get_by_id(id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Document not found: {}", id))?;
+    p904_sales_data::projection_builder::from_ym_returns(&document, &id.to_string()).await
+}
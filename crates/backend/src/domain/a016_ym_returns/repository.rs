@@ -30,6 +30,7 @@ pub struct Model {
     pub source_meta_json: String,
     pub is_deleted: bool,
     pub is_posted: bool,
+    pub is_sandbox: bool,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
     pub version: i32,
@@ -90,6 +91,7 @@ impl From<Model> for YmReturn {
             state,
             source_meta,
             is_posted: m.is_posted,
+            is_sandbox: m.is_sandbox,
         }
     }
 }
@@ -142,8 +144,19 @@ pub async fn list_by_order_ids(order_ids: &[i64]) -> Result<Vec<YmReturn>> {
 }
 
 pub async fn upsert_document(aggregate: &YmReturn) -> Result<Uuid> {
+    upsert_document_with_conn(conn(), aggregate).await
+}
+
+pub async fn upsert_document_with_conn<C: ConnectionTrait>(
+    db: &C,
+    aggregate: &YmReturn,
+) -> Result<Uuid> {
     let uuid = aggregate.base.id.value();
-    let existing = get_by_return_id(aggregate.header.return_id).await?;
+    let existing: Option<YmReturn> = Entity::find()
+        .filter(Column::ReturnId.eq(aggregate.header.return_id))
+        .one(db)
+        .await?
+        .map(Into::into);
 
     let header_json = serde_json::to_string(&aggregate.header)?;
     let lines_json = serde_json::to_string(&aggregate.lines)?;
@@ -167,11 +180,12 @@ pub async fn upsert_document(aggregate: &YmReturn) -> Result<Uuid> {
             // is_posted хранится в struct-поле агрегата (а не в metadata):
             // именно его выставляют new_for_insert/post_document/unpost_document.
             is_posted: Set(aggregate.is_posted),
+            is_sandbox: Set(aggregate.is_sandbox),
             updated_at: Set(Some(aggregate.base.metadata.updated_at)),
             version: Set(aggregate.base.metadata.version + 1),
             created_at: sea_orm::ActiveValue::NotSet,
         };
-        active.update(conn()).await?;
+        active.update(db).await?;
         Ok(existing_uuid)
     } else {
         let active = ActiveModel {
@@ -187,11 +201,12 @@ pub async fn upsert_document(aggregate: &YmReturn) -> Result<Uuid> {
             source_meta_json: Set(source_meta_json),
             is_deleted: Set(aggregate.base.metadata.is_deleted),
             is_posted: Set(aggregate.is_posted),
+            is_sandbox: Set(aggregate.is_sandbox),
             created_at: Set(Some(aggregate.base.metadata.created_at)),
             updated_at: Set(Some(aggregate.base.metadata.updated_at)),
             version: Set(aggregate.base.metadata.version),
         };
-        active.insert(conn()).await?;
+        active.insert(db).await?;
         Ok(uuid)
     }
 }
@@ -217,6 +232,8 @@ pub struct YmReturnsListQuery {
     pub date_to: Option<String>,
     pub return_type: Option<String>,
     pub connection_mp_ref: Option<String>,
+    /// Организация (активная организация пользователя)
+    pub organization_id: Option<String>,
     pub search_return_id: Option<String>,
     pub search_order_id: Option<String>,
     pub sort_by: String,
@@ -265,6 +282,12 @@ pub async fn list_sql(query: YmReturnsListQuery) -> Result<YmReturnsListResult>
             ));
         }
     }
+    if let Some(ref organization_id) = query.organization_id {
+        conditions.push(format!(
+            "LOWER(TRIM(REPLACE(COALESCE(json_extract(r.header_json, '$.organization_id'), ''), '\"', ''))) = LOWER(TRIM(REPLACE('{}', '\"', '')))",
+            organization_id.replace('\'', "''")
+        ));
+    }
     if let Some(ref search_return_id) = query.search_return_id {
         if !search_return_id.is_empty() {
             conditions.push(format!(
@@ -5,8 +5,9 @@ use contracts::domain::a020_wb_promotion::aggregate::{
     WbPromotionSourceMeta,
 };
 use contracts::domain::common::{BaseAggregate, EntityMetadata};
+use contracts::system::document_audit::DocumentAuditAction;
 use sea_orm::entity::prelude::*;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set, TransactionTrait};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -214,6 +215,7 @@ pub async fn upsert_document(document: &WbPromotion) -> Result<Uuid> {
     }
 }
 
+/// Проведение/отмена и запись журнала «История» — одной транзакцией.
 pub async fn set_posted(id: Uuid, is_posted: bool) -> Result<()> {
     let db = get_connection();
     let id_str = id.to_string();
@@ -222,7 +224,16 @@ pub async fn set_posted(id: Uuid, is_posted: bool) -> Result<()> {
         let mut active_model: ActiveModel = model.into();
         active_model.is_posted = Set(is_posted);
         active_model.updated_at = Set(Some(Utc::now()));
-        Entity::update(active_model).exec(db).await?;
+        let action = if is_posted {
+            DocumentAuditAction::Posted
+        } else {
+            DocumentAuditAction::Unposted
+        };
+        let txn = db.begin().await?;
+        Entity::update(active_model).exec(&txn).await?;
+        crate::system::document_audit::record_with_conn(&txn, "a020_wb_promotion", &id_str, action)
+            .await?;
+        txn.commit().await?;
     }
     Ok(())
 }
@@ -2,8 +2,11 @@ use super::repository;
 use anyhow::Result;
 use contracts::domain::a021_production_output::aggregate::ProductionOutput;
 use contracts::domain::common::AggregateId;
+use contracts::system::document_audit::DocumentAuditAction;
 use uuid::Uuid;
 
+use crate::system::document_audit;
+
 pub use repository::{ProductionOutputListQuery, ProductionOutputListResult};
 
 /// Сохранить или обновить документ из API
@@ -69,6 +72,12 @@ pub async fn post_document(id: Uuid) -> Result<()> {
 
     doc.base.metadata.is_posted = true;
     repository::upsert_document(&doc).await?;
+    document_audit::record(
+        "a021_production_output",
+        &id.to_string(),
+        DocumentAuditAction::Posted,
+    )
+    .await?;
     crate::projections::p912_nomenclature_costs::service::project_production_output(&doc).await?;
     tracing::info!("Posted production output document: {}", id);
     Ok(())
@@ -82,6 +91,12 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
 
     doc.base.metadata.is_posted = false;
     repository::upsert_document(&doc).await?;
+    document_audit::record(
+        "a021_production_output",
+        &id.to_string(),
+        DocumentAuditAction::Unposted,
+    )
+    .await?;
     crate::projections::p912_nomenclature_costs::service::remove_by_registrator(
         "a021_production_output",
         &id.to_string(),
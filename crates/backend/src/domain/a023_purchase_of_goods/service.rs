@@ -1,8 +1,11 @@
 use super::repository;
 use anyhow::Result;
 use contracts::domain::a023_purchase_of_goods::aggregate::PurchaseOfGoods;
+use contracts::system::document_audit::DocumentAuditAction;
 use uuid::Uuid;
 
+use crate::system::document_audit;
+
 pub use repository::{PurchaseOfGoodsListQuery, PurchaseOfGoodsListResult};
 
 /// Сохранить или обновить документ из OData
@@ -23,6 +26,12 @@ pub async fn post_document(id: Uuid) -> Result<()> {
 
     doc.base.metadata.is_posted = true;
     repository::upsert_document(&doc).await?;
+    document_audit::record(
+        "a023_purchase_of_goods",
+        &id.to_string(),
+        DocumentAuditAction::Posted,
+    )
+    .await?;
     crate::projections::p912_nomenclature_costs::service::project_purchase_of_goods(&doc).await?;
     Ok(())
 }
@@ -34,6 +43,12 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
 
     doc.base.metadata.is_posted = false;
     repository::upsert_document(&doc).await?;
+    document_audit::record(
+        "a023_purchase_of_goods",
+        &id.to_string(),
+        DocumentAuditAction::Unposted,
+    )
+    .await?;
     crate::projections::p912_nomenclature_costs::service::remove_by_registrator(
         "a023_purchase_of_goods",
         &id.to_string(),
@@ -6,6 +6,7 @@ use contracts::domain::a026_wb_advert_daily::aggregate::{
 };
 use contracts::domain::common::AggregateId;
 use contracts::shared::analytics::TurnoverLayer;
+use contracts::system::document_audit::DocumentAuditAction;
 use sea_orm::TransactionTrait;
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -646,6 +647,13 @@ pub async fn post_document(id: Uuid) -> Result<()> {
     )
     .await?;
 
+    crate::system::document_audit::record_with_conn(
+        &txn,
+        REGISTRATOR_TYPE,
+        &id.to_string(),
+        DocumentAuditAction::Posted,
+    )
+    .await?;
     txn.commit().await?;
     tracing::info!(
         "a026 post_document: SQL transaction committed document_id={}, elapsed_ms={}",
@@ -705,6 +713,13 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
     )
     .await?;
 
+    crate::system::document_audit::record_with_conn(
+        &txn,
+        REGISTRATOR_TYPE,
+        &id.to_string(),
+        DocumentAuditAction::Unposted,
+    )
+    .await?;
     txn.commit().await?;
 
     Ok(())
@@ -464,7 +464,13 @@ pub async fn update_prepared_document_with_conn<C: ConnectionTrait>(
 }
 
 pub async fn get_by_id(id: Uuid) -> Result<Option<WbAdvertDaily>> {
-    let db = get_connection();
+    get_by_id_with_conn(get_connection(), id).await
+}
+
+pub async fn get_by_id_with_conn<C: ConnectionTrait>(
+    db: &C,
+    id: Uuid,
+) -> Result<Option<WbAdvertDaily>> {
     let model = Entity::find_by_id(id.to_string()).one(db).await?;
     Ok(model.map(Into::into))
 }
@@ -498,6 +504,8 @@ pub struct WbAdvertDailyListQuery {
     pub date_from: Option<String>,
     pub date_to: Option<String>,
     pub connection_id: Option<String>,
+    /// Активная организация пользователя (`None` — все)
+    pub organization_id: Option<String>,
     pub search_query: Option<String>,
     pub sort_by: String,
     pub sort_desc: bool,
@@ -531,8 +539,13 @@ pub struct WbAdvertDailyListResult {
 }
 
 pub async fn list_sql(query: WbAdvertDailyListQuery) -> Result<WbAdvertDailyListResult> {
-    let db = get_connection();
+    list_sql_with_conn(get_connection(), query).await
+}
 
+pub async fn list_sql_with_conn<C: ConnectionTrait>(
+    db: &C,
+    query: WbAdvertDailyListQuery,
+) -> Result<WbAdvertDailyListResult> {
     let mut conditions = vec!["d.is_deleted = 0".to_string()];
 
     if let Some(ref date_from) = query.date_from {
@@ -550,6 +563,9 @@ pub async fn list_sql(query: WbAdvertDailyListQuery) -> Result<WbAdvertDailyList
             conditions.push(format!("d.connection_id = '{}'", connection_id));
         }
     }
+    if let Some(ref organization_id) = query.organization_id {
+        conditions.push(organization_condition(organization_id));
+    }
     if let Some(ref search) = query.search_query {
         if !search.is_empty() {
             let escaped = search.replace('\'', "''");
@@ -666,9 +682,17 @@ pub struct WbAdvertDailyReportQuery {
     pub date_from: Option<String>,
     pub date_to: Option<String>,
     pub connection_id: Option<String>,
+    pub organization_id: Option<String>,
     pub search_query: Option<String>,
 }
 
+fn organization_condition(organization_id: &str) -> String {
+    format!(
+        "LOWER(TRIM(REPLACE(COALESCE(d.organization_id, ''), '\"', ''))) = LOWER(TRIM(REPLACE('{}', '\"', '')))",
+        organization_id.replace('\'', "''")
+    )
+}
+
 fn build_report_where_clause(query: &WbAdvertDailyReportQuery) -> String {
     let mut conditions = vec!["d.is_deleted = 0".to_string()];
 
@@ -687,6 +711,9 @@ fn build_report_where_clause(query: &WbAdvertDailyReportQuery) -> String {
             conditions.push(format!("d.connection_id = '{}'", connection_id));
         }
     }
+    if let Some(ref organization_id) = query.organization_id {
+        conditions.push(organization_condition(organization_id));
+    }
     if let Some(ref search) = query.search_query {
         if !search.is_empty() {
             let escaped = search.replace('\'', "''");
@@ -970,7 +997,104 @@ pub fn subtract_metrics(
 
 #[cfg(test)]
 mod tests {
-    use super::projection_registrator_ref;
+    use super::*;
+    use crate::system::org_context::ActiveOrganization;
+    use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+
+    const ORG_A: &str = "5f0c8a8e-1111-4a4a-9b9b-000000000001";
+    const ORG_B: &str = "5f0c8a8e-2222-4a4a-9b9b-000000000002";
+    const DOC_A: &str = "0b6f5f3e-aaaa-4c1e-8f00-000000000001";
+    const DOC_B: &str = "0b6f5f3e-bbbb-4c1e-8f00-000000000002";
+
+    /// Два документа одного дня: у организации A (id в кавычках и в верхнем
+    /// регистре, как пишут старые импорты) и у организации B.
+    async fn two_organizations() -> DatabaseConnection {
+        let mut options = ConnectOptions::new("sqlite::memory:");
+        options.max_connections(1);
+        let db = Database::connect(options).await.unwrap();
+        db.execute_unprepared(&format!(
+            "CREATE TABLE a006_connection_mp (id TEXT PRIMARY KEY, description TEXT);
+             CREATE TABLE a002_organization (id TEXT PRIMARY KEY, description TEXT);
+             CREATE TABLE a026_wb_advert_daily (
+                 id TEXT PRIMARY KEY, code TEXT DEFAULT '', description TEXT DEFAULT '',
+                 comment TEXT, advert_id INTEGER DEFAULT 0, document_no TEXT,
+                 document_date TEXT, connection_id TEXT, organization_id TEXT,
+                 marketplace_id TEXT DEFAULT '', lines_count INTEGER DEFAULT 0,
+                 total_views INTEGER DEFAULT 0, total_clicks INTEGER DEFAULT 0,
+                 total_orders INTEGER DEFAULT 0, total_sum REAL DEFAULT 0,
+                 total_sum_price REAL DEFAULT 0, header_json TEXT DEFAULT '',
+                 totals_json TEXT DEFAULT '', unattributed_totals_json TEXT DEFAULT '',
+                 lines_json TEXT DEFAULT '', source_meta_json TEXT DEFAULT '',
+                 fetched_at TEXT DEFAULT '', is_deleted INTEGER DEFAULT 0,
+                 is_posted INTEGER DEFAULT 0, has_linked_orders INTEGER DEFAULT 0,
+                 linked_orders_count INTEGER DEFAULT 0, linked_orders_json TEXT DEFAULT '',
+                 created_at TEXT, updated_at TEXT, version INTEGER DEFAULT 1);
+             INSERT INTO a006_connection_mp VALUES ('cab-a', 'Кабинет A'), ('cab-b', 'Кабинет B');
+             INSERT INTO a002_organization VALUES ('{ORG_A}', 'A'), ('{ORG_B}', 'B');
+             INSERT INTO a026_wb_advert_daily
+                 (id, document_no, document_date, connection_id, organization_id)
+             VALUES ('{DOC_A}', 'ADV-A', '2026-10-01', 'cab-a', '\"{org_a}\"'),
+                    ('{DOC_B}', 'ADV-B', '2026-10-01', 'cab-b', '{ORG_B}');",
+            org_a = ORG_A.to_uppercase(),
+        ))
+        .await
+        .unwrap();
+        db
+    }
+
+    fn list_query(organization_id: Option<&str>) -> WbAdvertDailyListQuery {
+        WbAdvertDailyListQuery {
+            date_from: None,
+            date_to: None,
+            connection_id: None,
+            organization_id: organization_id.map(str::to_string),
+            search_query: None,
+            sort_by: "document_no".to_string(),
+            sort_desc: false,
+            limit: 100,
+            offset: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn list_of_organization_never_returns_another_organization() {
+        let db = two_organizations().await;
+
+        let own = list_sql_with_conn(&db, list_query(Some(ORG_A)))
+            .await
+            .unwrap();
+        assert_eq!(own.total, 1);
+        let ids: Vec<&str> = own.items.iter().map(|row| row.id.as_str()).collect();
+        assert_eq!(ids, vec![DOC_A]);
+
+        let other = list_sql_with_conn(&db, list_query(Some(ORG_B)))
+            .await
+            .unwrap();
+        let ids: Vec<&str> = other.items.iter().map(|row| row.id.as_str()).collect();
+        assert_eq!(ids, vec![DOC_B]);
+
+        let all = list_sql_with_conn(&db, list_query(None)).await.unwrap();
+        assert_eq!(all.total, 2);
+    }
+
+    /// Та же проверка, что в обработчике карточки: документ чужой организации — 404.
+    async fn visible(db: &DatabaseConnection, active: &ActiveOrganization, id: &str) -> bool {
+        get_by_id_with_conn(db, Uuid::parse_str(id).unwrap())
+            .await
+            .unwrap()
+            .filter(|doc| active.allows(&doc.header.organization_id))
+            .is_some()
+    }
+
+    #[tokio::test]
+    async fn detail_of_another_organization_is_hidden() {
+        let db = two_organizations().await;
+        let active = ActiveOrganization(Some(ORG_A.to_string()));
+
+        assert!(visible(&db, &active, DOC_A).await);
+        assert!(!visible(&db, &active, DOC_B).await);
+        assert!(visible(&db, &ActiveOrganization::default(), DOC_B).await);
+    }
 
     #[test]
     fn projection_registrator_ref_matches_posting_format() {
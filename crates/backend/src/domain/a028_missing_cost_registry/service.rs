@@ -4,9 +4,12 @@ use chrono::{Datelike, NaiveDate, Utc};
 use contracts::domain::a028_missing_cost_registry::aggregate::{
     MissingCostRegistry, MissingCostRegistryLine, MissingCostRegistryUpdateDto,
 };
+use contracts::system::document_audit::DocumentAuditAction;
 use std::collections::BTreeSet;
 use uuid::Uuid;
 
+use crate::system::document_audit;
+
 pub use repository::{MissingCostRegistryListQuery, MissingCostRegistryListResult};
 
 const A028_REGISTRATOR_TYPE: &str = "a028_missing_cost_registry";
@@ -79,6 +82,12 @@ pub async fn post_document(id: Uuid) -> Result<()> {
     doc.base.metadata.is_posted = true;
     doc.before_write();
     repository::update_document(&doc).await?;
+    document_audit::record(
+        A028_REGISTRATOR_TYPE,
+        &id.to_string(),
+        DocumentAuditAction::Posted,
+    )
+    .await?;
     crate::projections::p912_nomenclature_costs::service::project_missing_cost_registry(&doc)
         .await?;
     Ok(())
@@ -92,6 +101,12 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
     doc.base.metadata.is_posted = false;
     doc.before_write();
     repository::update_document(&doc).await?;
+    document_audit::record(
+        A028_REGISTRATOR_TYPE,
+        &id.to_string(),
+        DocumentAuditAction::Unposted,
+    )
+    .await?;
     crate::projections::p912_nomenclature_costs::service::remove_by_registrator(
         A028_REGISTRATOR_TYPE,
         &id.to_string(),
@@ -30,6 +30,7 @@ pub(crate) fn a012_sale_date_upper_bound(business_date: &str) -> String {
 }
 
 use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
 
 fn conn() -> &'static sea_orm::DatabaseConnection {
     get_connection()
@@ -471,12 +472,13 @@ async fn fetch_a012_for_srids(
                 COALESCE(dealer_price_ut, 0.0) * COALESCE(ABS(qty), 1.0) AS dealer_total,
                 is_posted,
                 COALESCE(event_type, 'sale') AS event_type
-            FROM a012_wb_sales
+            FROM {source}
             WHERE connection_id = ?
               AND substr(COALESCE(sale_date, ''), 1, 10) <= ?
               AND document_no IN ({placeholders})
               AND is_deleted = 0
             "#,
+            source = projection_archive::source("a012_wb_sales", None),
         );
 
         let mut params: Vec<Value> = vec![sv(connection_id), sv(&sale_date_to)];
@@ -576,7 +578,8 @@ async fn fetch_a012_sale_on_close_without_fin_report(
     connection_id: &str,
     business_date: &str,
 ) -> Result<Vec<A012FinDateMismatch>> {
-    let sql = r#"
+    let sql = format!(
+        r#"
         SELECT
             a.id,
             a.document_no AS srid,
@@ -588,7 +591,7 @@ async fn fetch_a012_sale_on_close_without_fin_report(
                   AND p.connection_mp_ref = a.connection_id
                   AND p.rr_dt != ?
             ) AS fin_report_date
-        FROM a012_wb_sales a
+        FROM {source} a
         WHERE a.connection_id = ?
           AND SUBSTR(COALESCE(a.sale_date, ''), 1, 10) = ?
           AND a.is_deleted = 0
@@ -606,10 +609,12 @@ async fn fetch_a012_sale_on_close_without_fin_report(
               WHERE p3.srid = a.document_no
                 AND p3.connection_mp_ref = a.connection_id
           )
-    "#;
+    "#,
+        source = projection_archive::source("a012_wb_sales", Some(business_date)),
+    );
     let stmt = Statement::from_sql_and_values(
         conn().get_database_backend(),
-        sql,
+        &sql,
         vec![
             sv(business_date),
             sv(connection_id),
@@ -237,6 +237,8 @@ pub struct ListQuery {
     pub date_from: Option<String>,
     pub date_to: Option<String>,
     pub include_archived: Option<bool>,
+    /// Организация кабинета: документ дня своей организации не хранит
+    pub organization_id: Option<String>,
     pub limit: Option<u64>,
     pub offset: Option<u64>,
 }
@@ -256,6 +258,13 @@ pub async fn list_paginated(query: ListQuery) -> Result<Vec<WbDayClose>> {
     if !query.include_archived.unwrap_or(true) {
         q = q.filter(Column::IsArchived.eq(false));
     }
+    if let Some(organization_id) = query.organization_id {
+        q = q.filter(Expr::cust_with_values(
+            "connection_id IN (SELECT id FROM a006_connection_mp \
+             WHERE LOWER(TRIM(REPLACE(COALESCE(organization_ref, ''), '\"', ''))) = LOWER(TRIM(REPLACE(?, '\"', ''))))",
+            [organization_id],
+        ));
+    }
 
     q = q
         .order_by_desc(Column::BusinessDate)
@@ -12,6 +12,7 @@
 use anyhow::Result;
 use chrono::Utc;
 use contracts::shared::analytics::TurnoverLayer;
+use contracts::system::document_audit::DocumentAuditAction;
 use sea_orm::TransactionTrait;
 use uuid::Uuid;
 
@@ -208,6 +209,13 @@ pub async fn post_document(id: Uuid) -> Result<()> {
         .await?;
     }
 
+    crate::system::document_audit::record_with_conn(
+        &txn,
+        REGISTRATOR_TYPE,
+        &id.to_string(),
+        DocumentAuditAction::Posted,
+    )
+    .await?;
     txn.commit().await?;
     Ok(())
 }
@@ -237,6 +245,13 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
         &registrator_ref,
     )
     .await?;
+    crate::system::document_audit::record_with_conn(
+        &txn,
+        REGISTRATOR_TYPE,
+        &id.to_string(),
+        DocumentAuditAction::Unposted,
+    )
+    .await?;
     txn.commit().await?;
     Ok(())
 }
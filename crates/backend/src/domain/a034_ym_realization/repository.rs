@@ -42,6 +42,7 @@ pub struct Model {
     pub fetched_at: String,
     pub is_deleted: bool,
     pub is_posted: bool,
+    pub is_sandbox: bool,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
     pub version: i32,
@@ -100,6 +101,7 @@ impl From<Model> for YmRealization {
             return_lines,
             source_meta,
             is_posted: m.is_posted,
+            is_sandbox: m.is_sandbox,
         }
     }
 }
@@ -136,6 +138,7 @@ fn to_active_model(
         fetched_at: Set(document.source_meta.fetched_at.clone()),
         is_deleted: Set(document.base.metadata.is_deleted),
         is_posted: Set(document.base.metadata.is_posted || document.is_posted),
+        is_sandbox: Set(document.is_sandbox),
         created_at: Set(created_at.or(Some(document.base.metadata.created_at))),
         updated_at: Set(Some(Utc::now())),
         version: Set(document.base.metadata.version),
@@ -229,6 +232,7 @@ pub async fn exists_with_conn<C: ConnectionTrait>(db: &C, id: &str) -> Result<bo
     Ok(Entity::find_by_id(id.to_string()).one(db).await?.is_some())
 }
 
+/// Id документов за период для перепроведения, без документов песочницы.
 pub async fn list_ids_by_period(
     date_from: &str,
     date_to: &str,
@@ -237,6 +241,7 @@ pub async fn list_ids_by_period(
     let db = get_connection();
     let mut query = Entity::find()
         .filter(Column::IsDeleted.eq(false))
+        .filter(Column::IsSandbox.eq(false))
         .filter(Column::DocumentDate.gte(date_from))
         .filter(Column::DocumentDate.lte(date_to));
     if only_posted {
@@ -4,6 +4,7 @@
 //! Идемпотентно: перед вставкой удаляются прежние события этого документа.
 
 use anyhow::Result;
+use contracts::system::document_audit::DocumentAuditAction;
 use sea_orm::TransactionTrait;
 use uuid::Uuid;
 
@@ -65,6 +66,13 @@ pub async fn post_document(id: Uuid) -> Result<()> {
         .await?;
     }
 
+    crate::system::document_audit::record_with_conn(
+        &txn,
+        REGISTRATOR_TYPE,
+        &id.to_string(),
+        DocumentAuditAction::Posted,
+    )
+    .await?;
     txn.commit().await?;
     tracing::info!(
         "a035 проведён: ордер {}, событий supplier_payment: {}",
@@ -93,6 +101,13 @@ pub async fn unpost_document(id: Uuid) -> Result<()> {
     )
     .await?;
 
+    crate::system::document_audit::record_with_conn(
+        &txn,
+        REGISTRATOR_TYPE,
+        &id.to_string(),
+        DocumentAuditAction::Unposted,
+    )
+    .await?;
     txn.commit().await?;
     tracing::info!(
         "a035 отмена проведения: ордер {}",
@@ -5,6 +5,7 @@ use contracts::domain::a036_wb_sales_funnel_daily::aggregate::{
     WbSalesFunnelDailyMetrics, WbSalesFunnelDailySourceMeta,
 };
 use contracts::domain::common::{BaseAggregate, EntityMetadata};
+use contracts::system::document_audit::DocumentAuditAction;
 use sea_orm::entity::prelude::*;
 use sea_orm::{ConnectionTrait, EntityTrait, QueryFilter, Set, Statement, TransactionTrait};
 use serde::{Deserialize, Serialize};
@@ -117,6 +118,13 @@ pub async fn post_document(id: Uuid) -> Result<()> {
     )
     .await?;
     funnel_repo::insert_many_with_conn(&txn, &rows).await?;
+    crate::system::document_audit::record_with_conn(
+        &txn,
+        "a036_wb_sales_funnel_daily",
+        &id.to_string(),
+        DocumentAuditAction::Posted,
+    )
+    .await?;
     txn.commit().await?;
     Ok(())
 }
@@ -1,3 +1,5 @@
 //! План счетов General Ledger.
 //! Re-export из shared/analytics для обратной совместимости.
-pub use crate::shared::analytics::account_registry::{get_account, ACCOUNT_REGISTRY};
+pub use crate::shared::analytics::account_registry::{
+    get_account, is_profit_loss_account, ACCOUNT_REGISTRY,
+};
@@ -76,7 +76,12 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
-    // 3.1 Reset stale Running rows from previous server process (sys_task_runs)
+    // 3.1 Load projection archive boundary (query routing to *_archive tables)
+    if let Err(e) = shared::data::projection_archive::load_boundary().await {
+        println!("⚠ Could not load projection archive boundary: {}\n", e);
+    }
+
+    // 3.2 Reset stale Running rows from previous server process (sys_task_runs)
     match system::tasks::runs_service::reset_stale_running_runs("Server restarted").await {
         Ok(n) if n > 0 => println!(
             "✓ Reset {} stale scheduled task run(s) (were Running after restart)\n",
@@ -86,6 +91,20 @@ async fn main() -> anyhow::Result<()> {
         Err(e) => println!("⚠ Could not reset stale task runs: {}\n", e),
     }
 
+    // 3.3 Export jobs that were in progress when the previous process stopped
+    match system::exports::service::fail_interrupted().await {
+        Ok(n) if n > 0 => println!("✓ Marked {} interrupted export job(s) as failed\n", n),
+        Ok(_) => {}
+        Err(e) => println!("⚠ Could not reset interrupted export jobs: {}\n", e),
+    }
+
+    // 3.4 Batch operations that were queued or running when the previous process stopped
+    match system::operations::service::fail_interrupted().await {
+        Ok(n) if n > 0 => println!("✓ Marked {} interrupted operation(s) as failed\n", n),
+        Ok(_) => {}
+        Err(e) => println!("⚠ Could not reset interrupted operations: {}\n", e),
+    }
+
     // 4. Ensure admin user exists
     println!("Step 4: Checking admin user...");
     match system::initialization::ensure_admin_user_exists().await {
@@ -119,6 +138,38 @@ async fn main() -> anyhow::Result<()> {
             } else {
                 println!("⚠  Mail: disabled ([mail].enabled not set in config.toml)\n");
             }
+            shared::config::set_telegram_config(cfg.telegram.clone());
+            if cfg.telegram.is_ready() {
+                println!("✓ Telegram: enabled (notifications bot configured)\n");
+            } else {
+                println!("⚠  Telegram: disabled ([telegram] not configured in config.toml)\n");
+            }
+            shared::config::set_environment_config(cfg.environment.clone());
+            println!(
+                "✓ Environment: {} ([environment].mode in config.toml)\n",
+                cfg.environment.mode.code()
+            );
+            shared::secrets::set_encryption_key(&cfg.secrets.encryption_key);
+            if shared::secrets::is_enabled() {
+                match domain::a006_connection_mp::service::encrypt_stored_keys().await {
+                    Ok(0) => println!("✓ Secrets: connection API keys encrypted\n"),
+                    Ok(n) => println!(
+                        "✓ Secrets: encrypted {} plaintext connection API key(s)\n",
+                        n
+                    ),
+                    Err(e) => println!("✗ Secrets: failed to encrypt connection API keys: {}\n", e),
+                }
+            } else {
+                println!("⚠  Secrets: connection API keys stored as plaintext ([secrets].encryption_key not set)\n");
+            }
+            shared::config::set_sandbox_config(cfg.sandbox.clone());
+            shared::config::set_http_retry_config(cfg.http_retry.clone());
+            if !cfg.sandbox.mock_base_url.trim().is_empty() {
+                println!(
+                    "✓ Sandbox: test connections use mock server {}\n",
+                    cfg.sandbox.mock_base_url
+                );
+            }
             cfg.scheduled_tasks.enabled
         }
         Err(e) => {
@@ -169,11 +220,26 @@ async fn main() -> anyhow::Result<()> {
         drop(worker);
     }
 
-    // Прунинг лога внешнего API. Намеренно не регламентное задание: планировщик
-    // может быть выключен в config.toml, и тогда задание просто не выполнилось бы.
+    // Прунинг лога внешнего API, ленты CDC и просроченных выгрузок. Намеренно не регламентные
+    // задания: планировщик может быть выключен в config.toml, и тогда они просто не выполнились бы.
     tokio::spawn(async {
         system::ext_api_log::service::run_prune_loop().await;
     });
+    tokio::spawn(async {
+        system::cdc::service::run_prune_loop().await;
+    });
+    tokio::spawn(async {
+        system::exports::service::run_purge_loop().await;
+    });
+    tokio::spawn(async {
+        system::scheduled_posts::service::run_loop().await;
+    });
+    tokio::spawn(async {
+        system::export_profiles::service::run_loop().await;
+    });
+    tokio::spawn(async {
+        system::quotas::service::run_flush_loop().await;
+    });
 
     // 5. Configure CORS
     println!("Step 8: Configuring CORS...");
@@ -186,7 +252,15 @@ async fn main() -> anyhow::Result<()> {
             Method::DELETE,
             Method::OPTIONS,
         ])
-        .allow_headers([header::CONTENT_TYPE, header::ACCEPT, header::AUTHORIZATION]);
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::ACCEPT,
+            header::AUTHORIZATION,
+            header::HeaderName::from_static(system::middleware::api_version::API_VERSION_HEADER),
+        ])
+        .expose_headers([header::HeaderName::from_static(
+            system::middleware::request_logger::REQUEST_ID_HEADER,
+        )]);
     println!("✓ CORS configured\n");
 
     // 6. Build app with routes
@@ -196,9 +270,14 @@ async fn main() -> anyhow::Result<()> {
         .merge(api::configure_business_routes())
         .fallback_service(ServeDir::new("dist"))
         .layer(middleware::from_fn(
-            system::middleware::request_logger::request_logger,
+            system::quotas::middleware::enforce_quotas,
         ))
-        .layer(cors);
+        .layer(middleware::from_fn(
+            system::middleware::request_logger::request_logger,
+        ));
+    // /api/v1/... переписывается в /api/... до маршрутизации; пути без версии
+    // получают заголовки Deprecation/Link.
+    let app = system::middleware::api_version::with_api_versioning(app).layer(cors);
     println!("✓ Routes configured\n");
 
     // 7. Start server
@@ -290,6 +369,9 @@ async fn main() -> anyhow::Result<()> {
     println!("╚══════════════════════════════════════════════════════════╝");
     println!("\n");
 
+    // /readyz начинает отвечать 200 только с этого момента
+    system::health::mark_started();
+
     // ConnectInfo нужен, чтобы ext_api_log писал IP вызывающего;
     // без него client_ip был бы всегда NULL.
     axum::serve(
@@ -19,8 +19,3 @@ pub async fn remove_by_registrator_ref(registrator_ref: &str) -> Result<()> {
     repository::delete_by_registrator_ref(registrator_ref).await?;
     Ok(())
 }
-
-pub async fn remove_by_registrator(registrator_type: &str, registrator_ref: &str) -> Result<()> {
-    repository::delete_by_registrator(registrator_type, registrator_ref).await?;
-    Ok(())
-}
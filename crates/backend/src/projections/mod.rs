@@ -16,3 +16,6 @@ pub mod p913_wb_advert_order_attr;
 pub mod p914_mp_finance_turnovers;
 pub mod p915_mp_order_events;
 pub mod p916_mp_sales_funnel_turnovers;
+pub mod p917_return_stock_ledger;
+pub mod p918_storage_cost_allocation;
+pub mod p919_daily_sales_summary;
@@ -193,6 +193,7 @@ async fn backfill_single_record(record: &repository::Model) -> Result<Option<Sal
         // Technical
         payload_version: record.payload_version,
         extra: record.extra.clone(),
+        warehouse_name: record.warehouse_name.clone(),
     };
 
     Ok(Some(updated_entry))
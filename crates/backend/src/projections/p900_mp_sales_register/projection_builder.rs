@@ -120,6 +120,7 @@ pub async fn from_ozon_fbs(
             // Technical
             payload_version: 1,
             extra: None,
+            warehouse_name: None,
         };
         entries.push(entry);
     }
@@ -199,6 +200,7 @@ pub async fn from_ozon_fbo(
             // Technical
             payload_version: 1,
             extra: None,
+            warehouse_name: None,
         };
         entries.push(entry);
     }
@@ -274,6 +276,7 @@ pub async fn from_wb_sales(
         // Technical
         payload_version: 1,
         extra: None,
+        warehouse_name: document.warehouse.warehouse_name.clone(),
     })
 }
 
@@ -365,6 +368,7 @@ pub async fn from_ym_order(
             // Technical
             payload_version: 1,
             extra: None,
+            warehouse_name: None,
         };
         entries.push(entry);
     }
@@ -444,5 +448,6 @@ pub async fn from_ozon_returns(
         // Technical
         payload_version: 1,
         extra: None,
+        warehouse_name: None,
     })
 }
@@ -1,12 +1,17 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use contracts::projections::p900_mp_sales_register::NomenclatureLinkStatus;
 use sea_orm::entity::prelude::*;
 use sea_orm::{
-    ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, Order, QueryFilter, QueryOrder,
+    QuerySelect, Set,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+
+const TABLE: &str = "p900_sales_register";
 
 /// Модель Sales Register entry
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -76,6 +81,9 @@ pub struct Model {
     pub payload_version: i32,
     #[sea_orm(nullable)]
     pub extra: Option<String>,
+    /// Склад продажи; заполняется только для WB
+    #[sea_orm(nullable)]
+    pub warehouse_name: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -134,6 +142,48 @@ pub struct SalesRegisterEntry {
     // Technical
     pub payload_version: i32,
     pub extra: Option<String>,
+    pub warehouse_name: Option<String>,
+}
+
+impl SalesRegisterEntry {
+    /// Строка таблицы в том виде, в каком её запишет upsert (для предпросмотра без записи).
+    pub fn to_model(&self, loaded_at_utc: String) -> Model {
+        Model {
+            marketplace: self.marketplace.clone(),
+            document_no: self.document_no.clone(),
+            line_id: self.line_id.clone(),
+            scheme: self.scheme.clone(),
+            document_type: self.document_type.clone(),
+            document_version: self.document_version,
+            connection_mp_ref: self.connection_mp_ref.clone(),
+            organization_ref: self.organization_ref.clone(),
+            marketplace_product_ref: self.marketplace_product_ref.clone(),
+            nomenclature_ref: self.nomenclature_ref.clone(),
+            registrator_ref: self.registrator_ref.clone(),
+            event_time_source: self.event_time_source.to_rfc3339(),
+            sale_date: self.sale_date.format("%Y-%m-%d").to_string(),
+            source_updated_at: self.source_updated_at.map(|dt| dt.to_rfc3339()),
+            status_source: self.status_source.clone(),
+            status_norm: self.status_norm.clone(),
+            seller_sku: self.seller_sku.clone(),
+            mp_item_id: self.mp_item_id.clone(),
+            barcode: self.barcode.clone(),
+            title: self.title.clone(),
+            qty: self.qty,
+            price_list: self.price_list,
+            discount_total: self.discount_total,
+            price_effective: self.price_effective,
+            amount_line: self.amount_line,
+            cost: self.cost,
+            dealer_price_ut: self.dealer_price_ut,
+            currency_code: self.currency_code.clone(),
+            is_fact: self.is_fact,
+            loaded_at_utc,
+            payload_version: self.payload_version,
+            extra: self.extra.clone(),
+            warehouse_name: self.warehouse_name.clone(),
+        }
+    }
 }
 
 /// Upsert записи в sales_register по NK (marketplace, document_no, line_id)
@@ -193,6 +243,7 @@ pub async fn upsert_entry_with_conn<C: ConnectionTrait>(
             loaded_at_utc: Set(now.to_rfc3339()),
             payload_version: Set(entry.payload_version),
             extra: Set(entry.extra.clone()),
+            warehouse_name: Set(entry.warehouse_name.clone()),
         };
         active.update(db).await?;
     } else {
@@ -230,6 +281,7 @@ pub async fn upsert_entry_with_conn<C: ConnectionTrait>(
             loaded_at_utc: Set(now.to_rfc3339()),
             payload_version: Set(entry.payload_version),
             extra: Set(entry.extra.clone()),
+            warehouse_name: Set(entry.warehouse_name.clone()),
         };
         active.insert(db).await?;
     }
@@ -245,7 +297,9 @@ pub async fn list_sales(limit: Option<u64>) -> Result<Vec<Model>> {
         query = query.limit(lim);
     }
 
-    let items = query.all(conn()).await?;
+    let items = projection_archive::route_select(TABLE, None, query)
+        .all(conn())
+        .await?;
     Ok(items)
 }
 
@@ -257,7 +311,9 @@ pub async fn get_by_marketplace(marketplace: &str, limit: Option<u64>) -> Result
         query = query.limit(lim);
     }
 
-    let items = query.all(conn()).await?;
+    let items = projection_archive::route_select(TABLE, None, query)
+        .all(conn())
+        .await?;
     Ok(items)
 }
 
@@ -267,10 +323,11 @@ pub async fn get_by_id(
     document_no: &str,
     line_id: &str,
 ) -> Result<Option<Model>> {
-    let item = Entity::find()
+    let query = Entity::find()
         .filter(Column::Marketplace.eq(marketplace))
         .filter(Column::DocumentNo.eq(document_no))
-        .filter(Column::LineId.eq(line_id))
+        .filter(Column::LineId.eq(line_id));
+    let item = projection_archive::route_select(TABLE, None, query)
         .one(conn())
         .await?;
     Ok(item)
@@ -285,6 +342,10 @@ pub async fn list_with_filters(
     connection_mp_ref: Option<String>,
     status_norm: Option<String>,
     seller_sku: Option<String>,
+    link_status: Option<NomenclatureLinkStatus>,
+    warehouse: Option<String>,
+    sort_by: Option<&str>,
+    sort_desc: bool,
     limit: i32,
     offset: i32,
 ) -> Result<(Vec<Model>, i32)> {
@@ -307,21 +368,70 @@ pub async fn list_with_filters(
     if let Some(sku) = seller_sku {
         query = query.filter(Column::SellerSku.eq(sku));
     }
+    match link_status {
+        Some(NomenclatureLinkStatus::Linked) => {
+            query = query.filter(
+                Condition::all()
+                    .add(Column::NomenclatureRef.is_not_null())
+                    .add(Column::NomenclatureRef.ne("")),
+            );
+        }
+        Some(NomenclatureLinkStatus::Unlinked) => {
+            query = query.filter(
+                Condition::any()
+                    .add(Column::NomenclatureRef.is_null())
+                    .add(Column::NomenclatureRef.eq("")),
+            );
+        }
+        None => {}
+    }
+    if let Some(warehouse) = warehouse.filter(|w| !w.trim().is_empty()) {
+        query = query.filter(Column::WarehouseName.contains(warehouse.trim()));
+    }
 
     // Count total
-    let total = query.clone().count(conn()).await? as i32;
+    let total =
+        projection_archive::count_routed(TABLE, Some(date_from), query.clone()).await? as i32;
 
     // Get page
-    let items = query
-        .order_by_desc(Column::SaleDate)
+    let query = apply_sort(query, sort_by, sort_desc)
         .limit(limit as u64)
-        .offset(offset as u64)
+        .offset(offset as u64);
+    let items = projection_archive::route_select(TABLE, Some(date_from), query)
         .all(conn())
         .await?;
 
     Ok((items, total))
 }
 
+/// Колонка сортировки списка по имени из `SALES_REGISTER_SORT_FIELDS`; неизвестное — дата.
+fn sort_column(sort_by: Option<&str>) -> Column {
+    match sort_by.unwrap_or_default() {
+        "marketplace" => Column::Marketplace,
+        "document_no" => Column::DocumentNo,
+        "seller_sku" => Column::SellerSku,
+        "title" => Column::Title,
+        "warehouse_name" => Column::WarehouseName,
+        "status_norm" => Column::StatusNorm,
+        "qty" => Column::Qty,
+        "amount_line" => Column::AmountLine,
+        _ => Column::SaleDate,
+    }
+}
+
+/// Сортировка списка; NK вторым ключом — стабильные страницы при равных значениях.
+fn apply_sort(
+    query: sea_orm::Select<Entity>,
+    sort_by: Option<&str>,
+    sort_desc: bool,
+) -> sea_orm::Select<Entity> {
+    let order = if sort_desc { Order::Desc } else { Order::Asc };
+    query
+        .order_by(sort_column(sort_by), order)
+        .order_by_asc(Column::DocumentNo)
+        .order_by_asc(Column::LineId)
+}
+
 /// Получить записи по диапазону дат (для статистики в service)
 pub async fn list_by_date_range(
     date_from: &str,
@@ -336,10 +446,192 @@ pub async fn list_by_date_range(
         query = query.filter(Column::Marketplace.eq(mp));
     }
 
-    let items = query.all(conn()).await?;
+    let items = projection_archive::route_select(TABLE, Some(date_from), query)
+        .all(conn())
+        .await?;
     Ok(items)
 }
 
+/// Документы-регистраторы с записями за период: `(document_type, registrator_ref)`.
+pub async fn list_registrators_by_period(
+    date_from: &str,
+    date_to: &str,
+) -> Result<Vec<(String, String)>> {
+    let sql = format!(
+        "SELECT DISTINCT document_type, registrator_ref FROM {} \
+         WHERE sale_date >= ? AND sale_date <= ? AND registrator_ref <> ''",
+        projection_archive::source(TABLE, Some(date_from))
+    );
+    let stmt = sea_orm::Statement::from_sql_and_values(
+        conn().get_database_backend(),
+        &sql,
+        vec![date_from.into(), date_to.into()],
+    );
+    let rows = conn().query_all(stmt).await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some((
+                row.try_get::<String>("", "document_type").ok()?,
+                row.try_get::<String>("", "registrator_ref").ok()?,
+            ))
+        })
+        .collect())
+}
+
+/// Удалить все записи периода, включая архив (пересборка проекции с нуля)
+pub async fn delete_by_period(date_from: &str, date_to: &str) -> Result<u64> {
+    projection_archive::delete_period_with_conn(conn(), TABLE, date_from, date_to).await
+}
+
+/// Продажи товара МП по дням: `(sale_date, qty, revenue)`, только дни с записями
+pub async fn daily_totals_by_product(
+    marketplace_product_ref: &str,
+    date_from: &str,
+    date_to: &str,
+) -> Result<Vec<(String, f64, f64)>> {
+    let sql = format!(
+        "SELECT sale_date, \
+                SUM(qty) AS qty, \
+                SUM(COALESCE(amount_line, 0)) AS revenue \
+         FROM {} p900 \
+         WHERE marketplace_product_ref = ? \
+           AND sale_date >= ? \
+           AND sale_date <= ? \
+         GROUP BY sale_date \
+         ORDER BY sale_date",
+        projection_archive::source(TABLE, Some(date_from))
+    );
+    let stmt = sea_orm::Statement::from_sql_and_values(
+        conn().get_database_backend(),
+        &sql,
+        [
+            marketplace_product_ref.into(),
+            date_from.into(),
+            date_to.into(),
+        ],
+    );
+    let rows = conn().query_all(stmt).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            (
+                row.try_get("", "sale_date").unwrap_or_default(),
+                row.try_get("", "qty").unwrap_or_default(),
+                row.try_get("", "revenue").unwrap_or_default(),
+            )
+        })
+        .collect())
+}
+
+/// Месяц когорты запусков × календарный месяц продаж.
+#[derive(Debug, Clone)]
+pub struct LaunchCohortMonthRow {
+    pub launch_month: String,
+    pub sale_month: String,
+    pub active_skus: i64,
+    pub revenue: f64,
+    /// Выручка минус себестоимость (`cost` хранится со знаком: продажа — минус).
+    pub margin: f64,
+}
+
+/// Первая продажа товара (a007) за всю историю p900, включая архив.
+/// `organization_ref` — активная организация пользователя (`None` — все).
+fn launches_cte(
+    connection_mp_ref: Option<&str>,
+    organization_ref: Option<&str>,
+) -> (String, Vec<sea_orm::Value>) {
+    let mut sql = format!(
+        "WITH launches AS ( \
+             SELECT marketplace_product_ref, substr(MIN(sale_date), 1, 7) AS launch_month \
+             FROM {} p900 \
+             WHERE marketplace_product_ref IS NOT NULL \
+               AND marketplace_product_ref <> ''",
+        projection_archive::source(TABLE, None)
+    );
+    let mut values = Vec::new();
+    if let Some(connection_mp_ref) = connection_mp_ref {
+        sql.push_str(" AND connection_mp_ref = ?");
+        values.push(connection_mp_ref.into());
+    }
+    if let Some(organization_ref) = organization_ref {
+        sql.push_str(" AND organization_ref = ?");
+        values.push(organization_ref.into());
+    }
+    sql.push_str(" GROUP BY marketplace_product_ref) ");
+    (sql, values)
+}
+
+/// Число SKU в когортах с месяцем запуска `launch_from..=launch_to` (`YYYY-MM`).
+pub async fn launch_cohort_sizes(
+    launch_from: &str,
+    launch_to: &str,
+    connection_mp_ref: Option<&str>,
+    organization_ref: Option<&str>,
+) -> Result<Vec<(String, i64)>> {
+    let (cte, mut values) = launches_cte(connection_mp_ref, organization_ref);
+    let sql = format!(
+        "{cte}SELECT launch_month, COUNT(*) AS sku_count \
+         FROM launches \
+         WHERE launch_month >= ? AND launch_month <= ? \
+         GROUP BY launch_month \
+         ORDER BY launch_month"
+    );
+    values.push(launch_from.into());
+    values.push(launch_to.into());
+    let stmt = sea_orm::Statement::from_sql_and_values(conn().get_database_backend(), &sql, values);
+    let rows = conn().query_all(stmt).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            (
+                row.try_get("", "launch_month").unwrap_or_default(),
+                row.try_get("", "sku_count").unwrap_or_default(),
+            )
+        })
+        .collect())
+}
+
+/// Выручка и маржа когорт запусков по календарным месяцам продаж.
+pub async fn launch_cohort_months(
+    launch_from: &str,
+    launch_to: &str,
+    connection_mp_ref: Option<&str>,
+    organization_ref: Option<&str>,
+) -> Result<Vec<LaunchCohortMonthRow>> {
+    let (cte, mut values) = launches_cte(connection_mp_ref, organization_ref);
+    let date_from = format!("{launch_from}-01");
+    let sql = format!(
+        "{cte}SELECT l.launch_month, \
+                substr(p900.sale_date, 1, 7) AS sale_month, \
+                COUNT(DISTINCT p900.marketplace_product_ref) AS active_skus, \
+                SUM(COALESCE(p900.amount_line, 0)) AS revenue, \
+                SUM(COALESCE(p900.amount_line, 0) + COALESCE(p900.cost, 0) * ABS(p900.qty)) AS margin \
+         FROM launches l \
+         JOIN {} p900 ON p900.marketplace_product_ref = l.marketplace_product_ref \
+         WHERE l.launch_month >= ? AND l.launch_month <= ? \
+           AND p900.sale_date >= ? \
+         GROUP BY l.launch_month, sale_month \
+         ORDER BY l.launch_month, sale_month",
+        projection_archive::source(TABLE, Some(&date_from))
+    );
+    values.push(launch_from.into());
+    values.push(launch_to.into());
+    values.push(date_from.into());
+    let stmt = sea_orm::Statement::from_sql_and_values(conn().get_database_backend(), &sql, values);
+    let rows = conn().query_all(stmt).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| LaunchCohortMonthRow {
+            launch_month: row.try_get("", "launch_month").unwrap_or_default(),
+            sale_month: row.try_get("", "sale_month").unwrap_or_default(),
+            active_skus: row.try_get("", "active_skus").unwrap_or_default(),
+            revenue: row.try_get("", "revenue").unwrap_or_default(),
+            margin: row.try_get("", "margin").unwrap_or_default(),
+        })
+        .collect())
+}
+
 /// Получить все записи с NULL marketplace_product_ref (для backfill)
 pub async fn get_records_with_null_product_ref() -> Result<Vec<Model>> {
     let items = Entity::find()
@@ -352,8 +644,8 @@ pub async fn get_records_with_null_product_ref() -> Result<Vec<Model>> {
 
 /// Получить все записи проекции для документа-регистратора
 pub async fn get_by_registrator(registrator_ref: &str) -> Result<Vec<Model>> {
-    let items = Entity::find()
-        .filter(Column::RegistratorRef.eq(registrator_ref))
+    let query = Entity::find().filter(Column::RegistratorRef.eq(registrator_ref));
+    let items = projection_archive::route_select(TABLE, None, query)
         .all(conn())
         .await?;
     Ok(items)
@@ -372,5 +664,8 @@ pub async fn delete_by_registrator_with_conn<C: ConnectionTrait>(
         .filter(Column::RegistratorRef.eq(registrator_ref))
         .exec(db)
         .await?;
-    Ok(result.rows_affected)
+    let archived =
+        projection_archive::delete_archived_by_registrator_with_conn(db, TABLE, registrator_ref)
+            .await?;
+    Ok(result.rows_affected + archived)
 }
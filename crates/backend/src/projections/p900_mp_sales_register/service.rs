@@ -1,78 +1,11 @@
-use super::{projection_builder, repository};
+use super::repository;
 use anyhow::Result;
-use contracts::domain::a009_ozon_returns::aggregate::OzonReturns;
-use contracts::domain::a010_ozon_fbs_posting::aggregate::OzonFbsPosting;
-use contracts::domain::a011_ozon_fbo_posting::aggregate::OzonFboPosting;
-use contracts::domain::a013_ym_order::aggregate::YmOrder;
-use contracts::projections::p900_mp_sales_register::{DailyStat, MarketplaceStat};
+use contracts::projections::p900_mp_sales_register::{
+    DailyStat, MarketplaceStat, NomenclatureLinkStatus, SkuSparklinePoint, SkuSparklineResponse,
+};
 use std::collections::HashMap;
-use uuid::Uuid;
 
-/// Проецировать OZON FBS Posting в Sales Register
-pub async fn project_ozon_fbs(document: &OzonFbsPosting, document_id: Uuid) -> Result<()> {
-    let entries = projection_builder::from_ozon_fbs(document, &document_id.to_string()).await?;
-
-    for entry in entries {
-        repository::upsert_entry(&entry).await?;
-    }
-
-    tracing::info!(
-        "Projected OZON FBS document {} into Sales Register ({} lines)",
-        document.header.document_no,
-        document.lines.len()
-    );
-
-    Ok(())
-}
-
-/// Проецировать OZON FBO Posting в Sales Register
-pub async fn project_ozon_fbo(document: &OzonFboPosting, document_id: Uuid) -> Result<()> {
-    let entries = projection_builder::from_ozon_fbo(document, &document_id.to_string()).await?;
-
-    for entry in entries {
-        repository::upsert_entry(&entry).await?;
-    }
-
-    tracing::info!(
-        "Projected OZON FBO document {} into Sales Register ({} lines)",
-        document.header.document_no,
-        document.lines.len()
-    );
-
-    Ok(())
-}
-
-/// Проецировать YM Order в Sales Register
-pub async fn project_ym_order(document: &YmOrder, document_id: Uuid) -> Result<()> {
-    let entries = projection_builder::from_ym_order(document, &document_id.to_string()).await?;
-
-    for entry in entries {
-        repository::upsert_entry(&entry).await?;
-    }
-
-    tracing::info!(
-        "Projected YM Order {} into Sales Register ({} lines)",
-        document.header.document_no,
-        document.lines.len()
-    );
-
-    Ok(())
-}
-
-/// Проецировать OZON Returns (возвраты) в Sales Register
-/// ВАЖНО: Создает запись с отрицательными значениями qty и amount
-pub async fn project_ozon_returns(document: &OzonReturns, document_id: Uuid) -> Result<()> {
-    let entry = projection_builder::from_ozon_returns(document, &document_id.to_string()).await?;
-    repository::upsert_entry(&entry).await?;
-
-    tracing::info!(
-        "Projected OZON Return {} into Sales Register (negative qty: {})",
-        document.return_id,
-        document.quantity
-    );
-
-    Ok(())
-}
+use crate::system::org_context::ActiveOrganization;
 
 /// Получить список продаж
 pub async fn list_sales(limit: Option<u64>) -> Result<Vec<repository::Model>> {
@@ -100,6 +33,10 @@ pub async fn list_with_filters(
     connection_mp_ref: Option<String>,
     status_norm: Option<String>,
     seller_sku: Option<String>,
+    link_status: Option<NomenclatureLinkStatus>,
+    warehouse: Option<String>,
+    sort_by: Option<&str>,
+    sort_desc: bool,
     limit: i32,
     offset: i32,
 ) -> Result<(Vec<repository::Model>, i32)> {
@@ -111,6 +48,10 @@ pub async fn list_with_filters(
         connection_mp_ref,
         status_norm,
         seller_sku,
+        link_status,
+        warehouse,
+        sort_by,
+        sort_desc,
         limit,
         offset,
     )
@@ -145,9 +86,13 @@ pub async fn calculate_daily_stats(
     date_from: &str,
     date_to: &str,
     marketplace: Option<String>,
+    active_org: &ActiveOrganization,
 ) -> Result<Vec<DailyStat>> {
     // Получаем сырые данные из repository
     let items = repository::list_by_date_range(date_from, date_to, marketplace).await?;
+    let items = items
+        .into_iter()
+        .filter(|item| active_org.allows(&item.organization_ref));
 
     // Группировка по датам
     let mut stats_map: HashMap<String, DailyStat> = HashMap::new();
@@ -176,9 +121,13 @@ pub async fn calculate_daily_stats(
 pub async fn calculate_marketplace_stats(
     date_from: &str,
     date_to: &str,
+    active_org: &ActiveOrganization,
 ) -> Result<Vec<MarketplaceStat>> {
     // Получаем сырые данные из repository
     let items = repository::list_by_date_range(date_from, date_to, None).await?;
+    let items = items
+        .into_iter()
+        .filter(|item| active_org.allows(&item.organization_ref));
 
     // Группировка по маркетплейсам
     let mut stats_map: HashMap<String, MarketplaceStat> = HashMap::new();
@@ -202,3 +151,80 @@ pub async fn calculate_marketplace_stats(
 
     Ok(result)
 }
+
+/// Окно sparkline по умолчанию и предел, чтобы endpoint оставался лёгким
+pub const SPARKLINE_DEFAULT_DAYS: u32 = 30;
+const SPARKLINE_MAX_DAYS: u32 = 365;
+
+/// Продажи товара МП по дням за `days` дней, заканчивая `date_to` включительно
+pub async fn sku_sparkline(
+    marketplace_product_ref: &str,
+    date_to: chrono::NaiveDate,
+    days: u32,
+) -> Result<SkuSparklineResponse> {
+    let days = days.clamp(1, SPARKLINE_MAX_DAYS);
+    let date_from = date_to - chrono::Duration::days(i64::from(days) - 1);
+    let date_from_str = date_from.format("%Y-%m-%d").to_string();
+    let date_to_str = date_to.format("%Y-%m-%d").to_string();
+
+    let rows =
+        repository::daily_totals_by_product(marketplace_product_ref, &date_from_str, &date_to_str)
+            .await?;
+    let points = fill_daily_points(date_from, days, &rows);
+    let total_qty: f64 = points.iter().map(|p| p.qty).sum();
+
+    Ok(SkuSparklineResponse {
+        marketplace_product_ref: marketplace_product_ref.to_string(),
+        date_from: date_from_str,
+        date_to: date_to_str,
+        avg_daily_qty: total_qty / f64::from(days),
+        total_qty,
+        points,
+    })
+}
+
+/// Развернуть агрегаты по дням в непрерывный ряд: пропущенные дни — нули
+fn fill_daily_points(
+    date_from: chrono::NaiveDate,
+    days: u32,
+    rows: &[(String, f64, f64)],
+) -> Vec<SkuSparklinePoint> {
+    let by_date: HashMap<&str, (f64, f64)> = rows
+        .iter()
+        .map(|(date, qty, revenue)| (date.as_str(), (*qty, *revenue)))
+        .collect();
+
+    (0..days)
+        .map(|offset| {
+            let date = (date_from + chrono::Duration::days(i64::from(offset)))
+                .format("%Y-%m-%d")
+                .to_string();
+            let (qty, revenue) = by_date.get(date.as_str()).copied().unwrap_or_default();
+            SkuSparklinePoint { date, qty, revenue }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_daily_points_inserts_zero_days() {
+        let from = chrono::NaiveDate::from_ymd_opt(2026, 2, 27).unwrap();
+        let rows = vec![
+            ("2026-02-27".to_string(), 2.0, 1000.0),
+            ("2026-03-01".to_string(), -1.0, -500.0),
+        ];
+
+        let points = fill_daily_points(from, 4, &rows);
+
+        let dates: Vec<&str> = points.iter().map(|p| p.date.as_str()).collect();
+        assert_eq!(
+            dates,
+            vec!["2026-02-27", "2026-02-28", "2026-03-01", "2026-03-02"]
+        );
+        let qty: Vec<f64> = points.iter().map(|p| p.qty).collect();
+        assert_eq!(qty, vec![2.0, 0.0, -1.0, 0.0]);
+    }
+}
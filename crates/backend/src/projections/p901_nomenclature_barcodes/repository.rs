@@ -170,6 +170,7 @@ pub async fn get_by_nomenclature_ref(
 
 /// Получить список штрихкодов с фильтрами и пагинацией (с JOIN для nomenclature_name)
 pub async fn list_with_filters(
+    barcode: Option<String>,
     nomenclature_ref: Option<String>,
     article: Option<String>,
     source: Option<String>,
@@ -184,6 +185,13 @@ pub async fn list_with_filters(
     let mut where_clauses = vec![];
     let mut params: Vec<sea_orm::Value> = vec![];
 
+    if let Some(ref barcode_val) = barcode {
+        if !barcode_val.trim().is_empty() {
+            where_clauses.push("b.barcode = ?");
+            params.push(barcode_val.trim().to_string().into());
+        }
+    }
+
     if let Some(ref nomenclature_ref_val) = nomenclature_ref {
         where_clauses.push("b.nomenclature_ref = ?");
         params.push(nomenclature_ref_val.clone().into());
@@ -0,0 +1,138 @@
+//! CSV-выгрузка финансового отчёта WB: все строки по фильтрам списка (без пагинации),
+//! все колонки таблицы `p903_wb_finance_report` с их оригинальными названиями.
+//! Формируется фоновой задачей выгрузки (`system::exports`); те же строки
+//! отдаёт потоковая выгрузка `GET /api/p903/finance-report/export`.
+
+use contracts::projections::p903_wb_finance_report::dto::WbFinanceReportListRequest;
+
+use super::repository;
+
+/// Строит CSV по фильтрам списка; возвращает содержимое и число строк.
+pub async fn build_csv(req: &WbFinanceReportListRequest) -> anyhow::Result<(Vec<u8>, usize)> {
+    let items = repository::list_all_with_filters(
+        &req.date_from,
+        &req.date_to,
+        req.nm_id,
+        req.sa_name.clone(),
+        req.connection_mp_ref.clone(),
+        req.organization_ref.clone(),
+        req.supplier_oper_name.clone(),
+        req.srid.clone(),
+        &req.sort_by,
+        req.sort_desc,
+    )
+    .await?;
+    let buffer = build_finance_report_csv(&items)?;
+    Ok((buffer, items.len()))
+}
+
+/// Заголовки = оригинальные имена колонок таблицы `p903_wb_finance_report`,
+/// в порядке объявления в `repository::Model`.
+pub(crate) const EXPORT_HEADERS: &[&str] = &[
+    "id",
+    "rr_dt",
+    "rrd_id",
+    "source_row_ref",
+    "connection_mp_ref",
+    "organization_ref",
+    "acquiring_fee",
+    "acquiring_percent",
+    "additional_payment",
+    "bonus_type_name",
+    "commission_percent",
+    "delivery_amount",
+    "delivery_rub",
+    "nm_id",
+    "a004_nomenclature_ref",
+    "penalty",
+    "ppvz_vw",
+    "ppvz_vw_nds",
+    "ppvz_sales_commission",
+    "quantity",
+    "rebill_logistic_cost",
+    "retail_amount",
+    "retail_price",
+    "retail_price_withdisc_rub",
+    "return_amount",
+    "sa_name",
+    "storage_fee",
+    "subject_name",
+    "supplier_oper_name",
+    "cashback_amount",
+    "ppvz_for_pay",
+    "ppvz_kvw_prc",
+    "ppvz_kvw_prc_base",
+    "srv_dbs",
+    "srid",
+    "loaded_at_utc",
+    "payload_version",
+];
+
+/// Десятичный разделитель — запятая (Excel/1С, ru-локаль). Полная точность.
+fn fmt_opt_f64(v: Option<f64>) -> String {
+    v.map(|x| x.to_string().replace('.', ","))
+        .unwrap_or_default()
+}
+
+/// Строка CSV по колонкам [`EXPORT_HEADERS`]; общая для фоновой и потоковой выгрузки.
+pub(crate) fn model_to_row(m: &repository::Model) -> Vec<String> {
+    vec![
+        m.id.clone(),
+        m.rr_dt.clone(),
+        m.rrd_id.to_string(),
+        m.source_row_ref.clone(),
+        m.connection_mp_ref.clone(),
+        m.organization_ref.clone(),
+        fmt_opt_f64(m.acquiring_fee),
+        fmt_opt_f64(m.acquiring_percent),
+        fmt_opt_f64(m.additional_payment),
+        m.bonus_type_name.clone().unwrap_or_default(),
+        fmt_opt_f64(m.commission_percent),
+        fmt_opt_f64(m.delivery_amount),
+        fmt_opt_f64(m.delivery_rub),
+        m.nm_id.map(|v| v.to_string()).unwrap_or_default(),
+        m.a004_nomenclature_ref.clone().unwrap_or_default(),
+        fmt_opt_f64(m.penalty),
+        fmt_opt_f64(m.ppvz_vw),
+        fmt_opt_f64(m.ppvz_vw_nds),
+        fmt_opt_f64(m.ppvz_sales_commission),
+        m.quantity.map(|v| v.to_string()).unwrap_or_default(),
+        fmt_opt_f64(m.rebill_logistic_cost),
+        fmt_opt_f64(m.retail_amount),
+        fmt_opt_f64(m.retail_price),
+        fmt_opt_f64(m.retail_price_withdisc_rub),
+        fmt_opt_f64(m.return_amount),
+        m.sa_name.clone().unwrap_or_default(),
+        fmt_opt_f64(m.storage_fee),
+        m.subject_name.clone().unwrap_or_default(),
+        m.supplier_oper_name.clone().unwrap_or_default(),
+        fmt_opt_f64(m.cashback_amount),
+        fmt_opt_f64(m.ppvz_for_pay),
+        fmt_opt_f64(m.ppvz_kvw_prc),
+        fmt_opt_f64(m.ppvz_kvw_prc_base),
+        m.srv_dbs.map(|v| v.to_string()).unwrap_or_default(),
+        m.srid.clone().unwrap_or_default(),
+        m.loaded_at_utc.clone(),
+        m.payload_version.to_string(),
+    ]
+}
+
+fn build_finance_report_csv(items: &[repository::Model]) -> anyhow::Result<Vec<u8>> {
+    let mut buffer: Vec<u8> = Vec::new();
+    // UTF-8 BOM — корректная кириллица при открытии в Excel.
+    buffer.extend_from_slice("\u{FEFF}".as_bytes());
+
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(b';')
+        .from_writer(&mut buffer);
+
+    wtr.write_record(EXPORT_HEADERS)?;
+
+    for m in items {
+        wtr.write_record(model_to_row(m))?;
+    }
+
+    wtr.flush()?;
+    drop(wtr);
+    Ok(buffer)
+}
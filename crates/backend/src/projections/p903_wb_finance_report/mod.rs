@@ -1,3 +1,4 @@
+pub mod export;
 pub mod general_ledger_builder;
 pub mod repository;
 pub mod representation;
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use chrono::{NaiveDate, Utc};
 use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::OnConflict;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
@@ -231,55 +232,119 @@ pub async fn upsert_entry(entry: &WbFinanceReportEntry) -> Result<()> {
         active_model.update(db).await?;
     } else {
         // Вставить новую запись
-        let new_model = ActiveModel {
-            rr_dt: Set(rr_dt_str),
-            rrd_id: Set(entry.rrd_id),
-            id: Set(make_entry_id()),
-            source_row_ref: Set(entry.source_row_ref.clone()),
-            connection_mp_ref: Set(entry.connection_mp_ref.clone()),
-            organization_ref: Set(entry.organization_ref.clone()),
-            acquiring_fee: Set(entry.acquiring_fee),
-            acquiring_percent: Set(entry.acquiring_percent),
-            additional_payment: Set(entry.additional_payment),
-            bonus_type_name: Set(entry.bonus_type_name.clone()),
-            commission_percent: Set(entry.commission_percent),
-            delivery_amount: Set(entry.delivery_amount),
-            delivery_rub: Set(entry.delivery_rub),
-            nm_id: Set(entry.nm_id),
-            a004_nomenclature_ref: Set(entry.a004_nomenclature_ref.clone()),
-            marketplace_product_ref: Set(None),
-            marketplace_order_ref: Set(None),
-            penalty: Set(entry.penalty),
-            ppvz_vw: Set(entry.ppvz_vw),
-            ppvz_vw_nds: Set(entry.ppvz_vw_nds),
-            ppvz_sales_commission: Set(entry.ppvz_sales_commission),
-            quantity: Set(entry.quantity),
-            rebill_logistic_cost: Set(entry.rebill_logistic_cost),
-            retail_amount: Set(entry.retail_amount),
-            retail_price: Set(entry.retail_price),
-            retail_price_withdisc_rub: Set(entry.retail_price_withdisc_rub),
-            return_amount: Set(entry.return_amount),
-            sa_name: Set(entry.sa_name.clone()),
-            storage_fee: Set(entry.storage_fee),
-            subject_name: Set(entry.subject_name.clone()),
-            supplier_oper_name: Set(entry.supplier_oper_name.clone()),
-            cashback_amount: Set(entry.cashback_amount),
-            ppvz_for_pay: Set(entry.ppvz_for_pay),
-            ppvz_kvw_prc: Set(entry.ppvz_kvw_prc),
-            ppvz_kvw_prc_base: Set(entry.ppvz_kvw_prc_base),
-            srv_dbs: Set(entry.srv_dbs),
-            srid: Set(entry.srid.clone()),
-            loaded_at_utc: Set(loaded_at_utc),
-            payload_version: Set(entry.payload_version),
-            extra: Set(entry.extra.clone()),
-        };
-
-        new_model.insert(db).await?;
+        new_active_model(entry, &loaded_at_utc).insert(db).await?;
     }
 
     Ok(())
 }
 
+/// Новая строка p903 из записи импорта (id генерируется).
+fn new_active_model(entry: &WbFinanceReportEntry, loaded_at_utc: &str) -> ActiveModel {
+    ActiveModel {
+        rr_dt: Set(entry.rr_dt.format("%Y-%m-%d").to_string()),
+        rrd_id: Set(entry.rrd_id),
+        id: Set(make_entry_id()),
+        source_row_ref: Set(entry.source_row_ref.clone()),
+        connection_mp_ref: Set(entry.connection_mp_ref.clone()),
+        organization_ref: Set(entry.organization_ref.clone()),
+        acquiring_fee: Set(entry.acquiring_fee),
+        acquiring_percent: Set(entry.acquiring_percent),
+        additional_payment: Set(entry.additional_payment),
+        bonus_type_name: Set(entry.bonus_type_name.clone()),
+        commission_percent: Set(entry.commission_percent),
+        delivery_amount: Set(entry.delivery_amount),
+        delivery_rub: Set(entry.delivery_rub),
+        nm_id: Set(entry.nm_id),
+        a004_nomenclature_ref: Set(entry.a004_nomenclature_ref.clone()),
+        marketplace_product_ref: Set(None),
+        marketplace_order_ref: Set(None),
+        penalty: Set(entry.penalty),
+        ppvz_vw: Set(entry.ppvz_vw),
+        ppvz_vw_nds: Set(entry.ppvz_vw_nds),
+        ppvz_sales_commission: Set(entry.ppvz_sales_commission),
+        quantity: Set(entry.quantity),
+        rebill_logistic_cost: Set(entry.rebill_logistic_cost),
+        retail_amount: Set(entry.retail_amount),
+        retail_price: Set(entry.retail_price),
+        retail_price_withdisc_rub: Set(entry.retail_price_withdisc_rub),
+        return_amount: Set(entry.return_amount),
+        sa_name: Set(entry.sa_name.clone()),
+        storage_fee: Set(entry.storage_fee),
+        subject_name: Set(entry.subject_name.clone()),
+        supplier_oper_name: Set(entry.supplier_oper_name.clone()),
+        cashback_amount: Set(entry.cashback_amount),
+        ppvz_for_pay: Set(entry.ppvz_for_pay),
+        ppvz_kvw_prc: Set(entry.ppvz_kvw_prc),
+        ppvz_kvw_prc_base: Set(entry.ppvz_kvw_prc_base),
+        srv_dbs: Set(entry.srv_dbs),
+        srid: Set(entry.srid.clone()),
+        loaded_at_utc: Set(loaded_at_utc.to_string()),
+        payload_version: Set(entry.payload_version),
+        extra: Set(entry.extra.clone()),
+    }
+}
+
+/// Пакетный upsert по `rrd_id` одним multi-row INSERT ... ON CONFLICT.
+///
+/// Используется массовой загрузкой истории: без чтения существующих строк.
+/// `a004_nomenclature_ref` и производные ссылки у существующих строк не
+/// затираются — их заполняет перепроведение периода.
+pub async fn upsert_entries_batch(entries: &[WbFinanceReportEntry]) -> Result<u64> {
+    if entries.is_empty() {
+        return Ok(0);
+    }
+    let db = get_connection();
+    let loaded_at_utc = Utc::now().to_rfc3339();
+    let models = entries
+        .iter()
+        .map(|entry| new_active_model(entry, &loaded_at_utc))
+        .collect::<Vec<_>>();
+    let on_conflict = OnConflict::column(Column::RrdId)
+        .update_columns([
+            Column::RrDt,
+            Column::SourceRowRef,
+            Column::ConnectionMpRef,
+            Column::OrganizationRef,
+            Column::AcquiringFee,
+            Column::AcquiringPercent,
+            Column::AdditionalPayment,
+            Column::BonusTypeName,
+            Column::CommissionPercent,
+            Column::DeliveryAmount,
+            Column::DeliveryRub,
+            Column::NmId,
+            Column::Penalty,
+            Column::PpvzVw,
+            Column::PpvzVwNds,
+            Column::PpvzSalesCommission,
+            Column::Quantity,
+            Column::RebillLogisticCost,
+            Column::RetailAmount,
+            Column::RetailPrice,
+            Column::RetailPriceWithdiscRub,
+            Column::ReturnAmount,
+            Column::SaName,
+            Column::StorageFee,
+            Column::SubjectName,
+            Column::SupplierOperName,
+            Column::CashbackAmount,
+            Column::PpvzForPay,
+            Column::PpvzKvwPrc,
+            Column::PpvzKvwPrcBase,
+            Column::SrvDbs,
+            Column::Srid,
+            Column::LoadedAtUtc,
+            Column::PayloadVersion,
+            Column::Extra,
+        ])
+        .to_owned();
+    let affected = Entity::insert_many(models)
+        .on_conflict(on_conflict)
+        .exec_without_returning(db)
+        .await?;
+    Ok(affected)
+}
+
 /// Получить список записей с фильтрами
 pub async fn list_with_filters(
     date_from: &str,
@@ -613,6 +678,17 @@ fn apply_sort(
     query
 }
 
+/// Итоги финансового отчета по одному srid
+#[derive(Debug, Clone, Default, sea_orm::FromQueryResult)]
+pub struct SridTotals {
+    pub count: i64,
+    pub ppvz_vw: f64,
+    pub ppvz_vw_nds: f64,
+    pub retail_amount: f64,
+    pub ppvz_for_pay: f64,
+    pub acquiring_fee: f64,
+}
+
 /// Поиск записей по srid
 pub async fn search_by_srid(srid: &str) -> Result<Vec<Model>> {
     let db = get_connection();
@@ -622,6 +698,82 @@ pub async fn search_by_srid(srid: &str) -> Result<Vec<Model>> {
     Ok(items)
 }
 
+/// Записи по списку rrd_id (ручные связи продаж a012)
+pub async fn list_by_rrd_ids(rrd_ids: &[i64]) -> Result<Vec<Model>> {
+    if rrd_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let items = Entity::find()
+        .filter(Column::RrdId.is_in(rrd_ids.iter().copied()))
+        .order_by_asc(Column::RrDt)
+        .order_by_asc(Column::RrdId)
+        .all(get_connection())
+        .await?;
+
+    Ok(items)
+}
+
+/// Кандидаты для ручной привязки к продаже: тот же nm_id в окне дат,
+/// без строк, которые уже находятся по SRID документа
+pub async fn search_link_candidates(
+    nm_id: i64,
+    date_from: &str,
+    date_to: &str,
+    exclude_srid: &str,
+    limit: u64,
+) -> Result<Vec<Model>> {
+    let items = Entity::find()
+        .filter(Column::NmId.eq(nm_id))
+        .filter(Column::RrDt.gte(date_from))
+        .filter(Column::RrDt.lte(date_to))
+        .filter(
+            sea_orm::Condition::any()
+                .add(Column::Srid.is_null())
+                .add(Column::Srid.ne(exclude_srid)),
+        )
+        .order_by_asc(Column::RrDt)
+        .order_by_asc(Column::RrdId)
+        .limit(limit)
+        .all(get_connection())
+        .await?;
+
+    Ok(items)
+}
+
+/// Итоги по записям с данным srid (считаются в SQL, без выгрузки строк)
+/// Итоги строк отчёта по srid; с `organization_ref` — только этой организации.
+pub async fn totals_by_srid(srid: &str, organization_ref: Option<&str>) -> Result<SridTotals> {
+    use sea_orm::{FromQueryResult, Statement};
+
+    let db = get_connection();
+    let stmt = Statement::from_sql_and_values(
+        db.get_database_backend(),
+        r#"
+        SELECT
+            COUNT(*) AS count,
+            COALESCE(SUM(ppvz_vw), 0.0) AS ppvz_vw,
+            COALESCE(SUM(ppvz_vw_nds), 0.0) AS ppvz_vw_nds,
+            COALESCE(SUM(retail_amount), 0.0) AS retail_amount,
+            COALESCE(SUM(ppvz_for_pay), 0.0) AS ppvz_for_pay,
+            COALESCE(SUM(acquiring_fee), 0.0) AS acquiring_fee
+        FROM p903_wb_finance_report
+        WHERE srid = ?
+          AND (? IS NULL OR LOWER(TRIM(REPLACE(COALESCE(organization_ref, ''), '"', ''))) = LOWER(TRIM(REPLACE(?, '"', ''))))
+        "#,
+        vec![
+            srid.into(),
+            organization_ref.map(str::to_string).into(),
+            organization_ref.map(str::to_string).into(),
+        ],
+    );
+
+    Ok(SridTotals::find_by_statement(stmt)
+        .one(db)
+        .await?
+        .unwrap_or_default())
+}
+
 pub async fn get_by_id(id: &str) -> Result<Option<Model>> {
     let db = get_connection();
 
@@ -55,6 +55,9 @@ struct SnapshotRow {
 
 type P903NaturalKey = i64;
 
+/// Ссылок регистраторов в одном `DELETE ... IN (...)` массовой пересборки.
+const REGISTRATOR_DELETE_CHUNK: usize = 500;
+
 fn snapshot_from_model(
     row: &crate::projections::p903_wb_finance_report::repository::Model,
 ) -> SnapshotRow {
@@ -514,30 +517,43 @@ async fn refresh_a004_links_from_a007(
 
     let db = get_connection();
     for model in models.iter_mut() {
-        let Some(nm_id) = model.nm_id else { continue };
-        let new_ref =
-            crate::domain::a007_marketplace_product::service::resolve_wb_nomenclature_ref(
-                connection_mp_ref,
-                nm_id,
-                model.sa_name.as_deref(),
-            )
-            .await?;
-        if model.a004_nomenclature_ref == new_ref {
+        if !resolve_a004_link(connection_mp_ref, model).await? {
             continue;
         }
         let am = ActiveModel {
             id: Set(model.id.clone()),
-            a004_nomenclature_ref: Set(new_ref.clone()),
+            a004_nomenclature_ref: Set(model.a004_nomenclature_ref.clone()),
             ..Default::default()
         };
         <crate::projections::p903_wb_finance_report::repository::Entity as EntityTrait>::update(am)
             .exec(db)
             .await?;
-        model.a004_nomenclature_ref = new_ref;
     }
     Ok(())
 }
 
+/// Подставляет в строку актуальное `a004_nomenclature_ref` из a007; `true`, если
+/// значение изменилось.
+async fn resolve_a004_link(
+    connection_mp_ref: &str,
+    model: &mut crate::projections::p903_wb_finance_report::repository::Model,
+) -> Result<bool> {
+    let Some(nm_id) = model.nm_id else {
+        return Ok(false);
+    };
+    let new_ref = crate::domain::a007_marketplace_product::service::resolve_wb_nomenclature_ref(
+        connection_mp_ref,
+        nm_id,
+        model.sa_name.as_deref(),
+    )
+    .await?;
+    if model.a004_nomenclature_ref == new_ref {
+        return Ok(false);
+    }
+    model.a004_nomenclature_ref = new_ref;
+    Ok(true)
+}
+
 /// Резолвит и заполняет (только если ещё пусто) производные ссылки строки p903:
 /// `marketplace_product_ref` (a007 по nm_id/артикулу) и `marketplace_order_ref`
 /// (a015 по srid). В нормальной ситуации значения уже заполнены и копируются
@@ -608,7 +624,14 @@ async fn refresh_marketplace_refs(
     Ok(())
 }
 
+/// Перепроведение p903 за период: GL и p914 строятся в памяти и грузятся массово
+/// через стейджинги (`shared::data::bulk_loader`), затем одна транзакция удаляет
+/// прежние проводки строк периода и переносит новые. Дозаполненные ссылки строк
+/// p903 сохраняются той же транзакцией. Возвращает число пар (кабинет, день).
 pub async fn rebuild_range_from_existing(date_from: &str, date_to: &str) -> Result<usize> {
+    use crate::projections::p903_wb_finance_report::general_ledger_builder;
+    use crate::shared::data::bulk_loader::BulkLoader;
+
     let parsed_from = NaiveDate::parse_from_str(date_from, "%Y-%m-%d")?;
     let parsed_to = NaiveDate::parse_from_str(date_to, "%Y-%m-%d")?;
     let rows = crate::projections::p903_wb_finance_report::repository::list_by_date_range(
@@ -617,19 +640,76 @@ pub async fn rebuild_range_from_existing(date_from: &str, date_to: &str) -> Resu
     .await?;
 
     let mut day_keys = rows
-        .into_iter()
-        .map(|row| (row.connection_mp_ref, row.rr_dt))
+        .iter()
+        .map(|row| (row.connection_mp_ref.clone(), row.rr_dt.clone()))
         .collect::<Vec<_>>();
     day_keys.sort();
     day_keys.dedup();
 
-    for (connection_mp_ref, date_str) in &day_keys {
-        let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")?;
-        if date < parsed_from || date > parsed_to {
-            continue;
+    let mut models = Vec::with_capacity(rows.len());
+    for row in rows {
+        let date = NaiveDate::parse_from_str(&row.rr_dt, "%Y-%m-%d")?;
+        if date >= parsed_from && date <= parsed_to {
+            models.push(row);
         }
-        rebuild_day_from_existing(connection_mp_ref, date).await?;
     }
+    if models.is_empty() {
+        return Ok(day_keys.len());
+    }
+
+    let mut p903_loader =
+        BulkLoader::<crate::projections::p903_wb_finance_report::repository::Entity>::begin()
+            .await?;
+    let mut gl_loader =
+        BulkLoader::<crate::projections::general_ledger::repository::Entity>::begin().await?;
+    let mut p914_loader =
+        BulkLoader::<crate::projections::p914_mp_finance_turnovers::repository::Entity>::begin()
+            .await?;
+
+    for model in models.iter_mut() {
+        let connection_mp_ref = model.connection_mp_ref.clone();
+        let a004_changed = resolve_a004_link(&connection_mp_ref, model).await?;
+        let refs_changed = resolve_and_set_marketplace_refs(model).await?;
+        if a004_changed || refs_changed {
+            p903_loader.push(model.clone()).await?;
+        }
+
+        let gl_entries = general_ledger_builder::build_general_ledger_entries(model, "")?;
+        let mut finance_turnovers =
+            general_ledger_builder::build_finance_turnover_entries(model, &gl_entries);
+        if !finance_turnovers.is_empty() {
+            enrich_wb_finance_turnovers(model, &mut finance_turnovers).await?;
+        }
+        gl_loader.extend(gl_entries).await?;
+        p914_loader.extend(finance_turnovers).await?;
+    }
+
+    let p903_staged = p903_loader.finish().await?;
+    let gl_staged = gl_loader.finish().await?;
+    let p914_staged = p914_loader.finish().await?;
+    let registrator_refs = gl_registrator_aliases_from_models(&models);
+
+    let txn = get_connection().begin().await?;
+    for chunk in registrator_refs.chunks(REGISTRATOR_DELETE_CHUNK) {
+        crate::general_ledger::repository::delete_by_registrator_refs_with_conn(&txn, chunk)
+            .await?;
+        crate::projections::p914_mp_finance_turnovers::repository::delete_by_registrator_refs_with_conn(
+            &txn, chunk,
+        )
+        .await?;
+    }
+    p903_staged.move_into_with_conn(&txn).await?;
+    let general_ledger_rows = gl_staged.move_into_with_conn(&txn).await?;
+    p914_staged.move_into_with_conn(&txn).await?;
+    txn.commit().await?;
+
+    tracing::info!(
+        "p903 bulk rebuild {}..{}: {} rows, {} GL entries",
+        date_from,
+        date_to,
+        models.len(),
+        general_ledger_rows
+    );
 
     Ok(day_keys.len())
 }
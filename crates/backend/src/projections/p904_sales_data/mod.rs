@@ -1,3 +1,4 @@
 pub mod projection_builder;
 pub mod repository;
+pub mod return_netting;
 pub mod service;
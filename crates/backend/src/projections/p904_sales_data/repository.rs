@@ -5,7 +5,11 @@ use sea_orm::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::shared::data::bulk_loader::StagedRows;
 use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+
+const TABLE: &str = "p904_sales_data";
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "p904_sales_data")]
@@ -96,8 +100,8 @@ pub async fn upsert_entry_with_conn<C: ConnectionTrait>(db: &C, entry: &Model) -
 }
 
 pub async fn get_by_registrator(registrator_ref: &str) -> Result<Vec<Model>> {
-    let items = Entity::find()
-        .filter(Column::RegistratorRef.eq(registrator_ref))
+    let query = Entity::find().filter(Column::RegistratorRef.eq(registrator_ref));
+    let items = projection_archive::route_select(TABLE, None, query)
         .all(conn())
         .await?;
     Ok(items)
@@ -115,7 +119,10 @@ pub async fn delete_by_registrator_with_conn<C: ConnectionTrait>(
         .filter(Column::RegistratorRef.eq(registrator_ref))
         .exec(db)
         .await?;
-    Ok(result.rows_affected)
+    let archived =
+        projection_archive::delete_archived_by_registrator_with_conn(db, TABLE, registrator_ref)
+            .await?;
+    Ok(result.rows_affected + archived)
 }
 
 pub async fn list(limit: Option<u64>) -> Result<Vec<Model>> {
@@ -125,7 +132,9 @@ pub async fn list(limit: Option<u64>) -> Result<Vec<Model>> {
         query = query.limit(lim);
     }
 
-    let items = query.all(conn()).await?;
+    let items = projection_archive::route_select(TABLE, None, query)
+        .all(conn())
+        .await?;
     Ok(items)
 }
 
@@ -145,17 +154,39 @@ pub struct ProjectionRegistrator {
     pub rows_count: i64,
 }
 
+/// Итоги списка Sales Data
+#[derive(Debug, Clone, Default, sea_orm::FromQueryResult)]
+pub struct SalesDataTotals {
+    pub count: i64,
+    pub customer_in: f64,
+    pub customer_out: f64,
+    pub coinvest_in: f64,
+    pub commission_out: f64,
+    pub acquiring_out: f64,
+    pub penalty_out: f64,
+    pub logistics_out: f64,
+    pub seller_out: f64,
+    pub price_full: f64,
+    pub price_list: f64,
+    pub price_return: f64,
+    pub commission_percent: f64,
+    pub coinvest_persent: f64,
+    pub total: f64,
+}
+
 /// List with filters: date range and connection_mp_ref
 pub async fn list_with_filters(
     date_from: Option<String>,
     date_to: Option<String>,
     connection_mp_ref: Option<String>,
+    organization_ref: Option<String>,
     limit: Option<u64>,
 ) -> Result<Vec<ModelWithCabinet>> {
     use sea_orm::{FromQueryResult, Statement};
 
     // Build SQL query manually for better control
-    let mut sql = r#"
+    let mut sql = format!(
+        r#"
         SELECT 
             p904.id,
             p904.registrator_ref,
@@ -183,29 +214,20 @@ pub async fn list_with_filters(
             p904.article,
             p904.posted_at,
             conn.description as connection_mp_name
-        FROM p904_sales_data p904
+        FROM {source} p904
         LEFT JOIN a006_connection_mp conn ON p904.connection_mp_ref = conn.id
         WHERE 1=1
-    "#
-    .to_string();
-
-    let mut params: Vec<sea_orm::Value> = vec![];
-
-    // Add date filters
-    if let Some(from) = &date_from {
-        sql.push_str(" AND substr(p904.date, 1, 10) >= ?");
-        params.push(from.clone().into());
-    }
-    if let Some(to) = &date_to {
-        sql.push_str(" AND substr(p904.date, 1, 10) <= ?");
-        params.push(to.clone().into());
-    }
+    "#,
+        source = projection_archive::source(TABLE, date_from.as_deref())
+    );
 
-    // Add connection_mp filter
-    if let Some(conn_ref) = &connection_mp_ref {
-        sql.push_str(&format!(" AND p904.connection_mp_ref = ?"));
-        params.push(conn_ref.clone().into());
-    }
+    let params = push_list_filters(
+        &mut sql,
+        date_from.as_deref(),
+        date_to.as_deref(),
+        connection_mp_ref.as_deref(),
+        organization_ref.as_deref(),
+    );
 
     // Add ordering and limit
     sql.push_str(" ORDER BY p904.date DESC");
@@ -288,6 +310,103 @@ pub async fn list_with_filters(
     Ok(items)
 }
 
+/// Фильтры списка (период, кабинет, организация) — общие для строк и итогов.
+fn push_list_filters(
+    sql: &mut String,
+    date_from: Option<&str>,
+    date_to: Option<&str>,
+    connection_mp_ref: Option<&str>,
+    organization_ref: Option<&str>,
+) -> Vec<sea_orm::Value> {
+    let mut params: Vec<sea_orm::Value> = vec![];
+
+    // Add date filters
+    if let Some(from) = date_from {
+        sql.push_str(" AND substr(p904.date, 1, 10) >= ?");
+        params.push(from.into());
+    }
+    if let Some(to) = date_to {
+        sql.push_str(" AND substr(p904.date, 1, 10) <= ?");
+        params.push(to.into());
+    }
+
+    // Add connection_mp filter
+    if let Some(conn_ref) = connection_mp_ref {
+        sql.push_str(" AND p904.connection_mp_ref = ?");
+        params.push(conn_ref.into());
+    }
+
+    // Организации в строке нет — она берётся из кабинета маркетплейса
+    if let Some(org_ref) = organization_ref {
+        sql.push_str(
+            " AND p904.connection_mp_ref IN (SELECT id FROM a006_connection_mp \
+             WHERE LOWER(TRIM(REPLACE(COALESCE(organization_ref, ''), '\"', ''))) = LOWER(TRIM(REPLACE(?, '\"', ''))))",
+        );
+        params.push(org_ref.into());
+    }
+
+    params
+}
+
+/// Итоги по тем же строкам, что отдаёт `list_with_filters` (с учётом лимита).
+///
+/// Считаются в SQL, чтобы список не суммировал тысячи строк при каждой отрисовке.
+pub async fn totals_with_filters(
+    date_from: Option<String>,
+    date_to: Option<String>,
+    connection_mp_ref: Option<String>,
+    organization_ref: Option<String>,
+    limit: Option<u64>,
+) -> Result<SalesDataTotals> {
+    use sea_orm::{FromQueryResult, Statement};
+
+    let mut inner = format!(
+        "SELECT p904.* FROM {} p904 WHERE 1=1",
+        projection_archive::source(TABLE, date_from.as_deref())
+    );
+    let params = push_list_filters(
+        &mut inner,
+        date_from.as_deref(),
+        date_to.as_deref(),
+        connection_mp_ref.as_deref(),
+        organization_ref.as_deref(),
+    );
+    inner.push_str(" ORDER BY p904.date DESC");
+    if let Some(lim) = limit {
+        inner.push_str(&format!(" LIMIT {}", lim));
+    }
+
+    let sql = format!(
+        r#"
+        SELECT
+            COUNT(*) AS count,
+            COALESCE(SUM(customer_in), 0.0) AS customer_in,
+            COALESCE(SUM(customer_out), 0.0) AS customer_out,
+            COALESCE(SUM(coinvest_in), 0.0) AS coinvest_in,
+            COALESCE(SUM(commission_out), 0.0) AS commission_out,
+            COALESCE(SUM(acquiring_out), 0.0) AS acquiring_out,
+            COALESCE(SUM(penalty_out), 0.0) AS penalty_out,
+            COALESCE(SUM(logistics_out), 0.0) AS logistics_out,
+            COALESCE(SUM(seller_out), 0.0) AS seller_out,
+            COALESCE(SUM(price_full), 0.0) AS price_full,
+            COALESCE(SUM(price_list), 0.0) AS price_list,
+            COALESCE(SUM(price_return), 0.0) AS price_return,
+            COALESCE(SUM(commission_percent), 0.0) AS commission_percent,
+            COALESCE(SUM(coinvest_persent), 0.0) AS coinvest_persent,
+            COALESCE(SUM(total), 0.0) AS total
+        FROM ({}) t
+    "#,
+        inner
+    );
+
+    let stmt = Statement::from_sql_and_values(sea_orm::DatabaseBackend::Sqlite, &sql, params);
+    let totals = SalesDataTotals::find_by_statement(stmt)
+        .one(conn())
+        .await?
+        .unwrap_or_default();
+    Ok(totals)
+}
+
 pub async fn list_registrators_by_period(
     date_from: &str,
     date_to: &str,
@@ -302,15 +421,14 @@ pub async fn list_registrators_by_period(
         rows_count: i64,
     }
 
-    let stmt = Statement::from_sql_and_values(
-        sea_orm::DatabaseBackend::Sqlite,
+    let sql = format!(
         r#"
             SELECT
                 registrator_ref,
                 registrator_type,
                 MAX(date) AS date,
                 COUNT(*) AS rows_count
-            FROM p904_sales_data
+            FROM {} p904
             WHERE substr(date, 1, 10) >= ?
               AND substr(date, 1, 10) <= ?
               AND registrator_ref <> ''
@@ -318,6 +436,11 @@ pub async fn list_registrators_by_period(
             GROUP BY registrator_ref, registrator_type
             ORDER BY MAX(date) DESC, registrator_type, registrator_ref
         "#,
+        projection_archive::source(TABLE, Some(date_from))
+    );
+    let stmt = Statement::from_sql_and_values(
+        sea_orm::DatabaseBackend::Sqlite,
+        &sql,
         vec![date_from.into(), date_to.into()],
     );
 
@@ -333,3 +456,25 @@ pub async fn list_registrators_by_period(
         })
         .collect())
 }
+
+/// Удалить все строки периода, включая архив (пересборка проекции с нуля).
+pub async fn delete_by_period(date_from: &str, date_to: &str) -> Result<u64> {
+    projection_archive::delete_period_with_conn(conn(), TABLE, date_from, date_to).await
+}
+
+/// Массовая пересборка: строки периода и документов из стейджинга заменяются
+/// загруженными. Возвращает (удалено, вставлено).
+pub async fn replace_from_staged_with_conn<C: ConnectionTrait>(
+    db: &C,
+    staged: &StagedRows,
+    date_from: &str,
+    date_to: &str,
+) -> Result<(u64, u64)> {
+    let mut deleted =
+        projection_archive::delete_period_with_conn(db, TABLE, date_from, date_to).await?;
+    deleted +=
+        projection_archive::delete_staged_registrators_with_conn(db, TABLE, staged.staging_table())
+            .await?;
+    let inserted = staged.move_into_with_conn(db).await?;
+    Ok((deleted, inserted))
+}
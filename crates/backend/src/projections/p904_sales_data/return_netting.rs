@@ -0,0 +1,219 @@
+//! Взаимозачёт возвратов WB с исходной продажей в P904.
+//!
+//! Собственные поля возврата a012 (price_effective, amount_line) часто не
+//! совпадают с продажей: комиссия и эквайринг возврата считаются по другой
+//! базе, и день возврата получает искажённую прибыль. При включённом режиме
+//! строка возврата строится как сторно строк P904 исходной продажи (та же
+//! пара srid + артикул): все суммы, включая комиссию и себестоимость, с
+//! обратным знаком, пропорционально количеству возврата.
+//!
+//! Если проведённой продажи нет, возврат проецируется по-старому, а сам
+//! документ попадает в отчёт несопоставленных возвратов.
+
+use anyhow::Result;
+use chrono::Utc;
+use contracts::domain::a012_wb_sales::aggregate::WbSales;
+use contracts::projections::p904_sales_data::dto::{
+    ReturnNettingMode, UnmatchedReturnDto, UnmatchedReturnsResponse,
+};
+use uuid::Uuid;
+
+use super::repository::{self, Model};
+use crate::shared::marketplaces::wildberries::datetime::wb_business_date_str;
+
+/// Строки P904 для возврата, зачтённого против продажи.
+/// `None` — режим выключен или продажа не найдена/не проведена: вызывающий
+/// оставляет обычную проекцию возврата.
+pub async fn build_netted_entries(
+    document: &WbSales,
+    document_id: &str,
+) -> Result<Option<Vec<Model>>> {
+    let mode = crate::system::settings::service::get_p904_return_netting_mode().await?;
+    if mode == ReturnNettingMode::Off || !document.is_customer_return {
+        return Ok(None);
+    }
+
+    let Some(sale) = crate::domain::a012_wb_sales::repository::find_sale_for_return(
+        &document.header.document_no,
+        &document.line.supplier_article,
+    )
+    .await?
+    else {
+        return Ok(None);
+    };
+    if !sale.is_posted || sale.id == document_id {
+        return Ok(None);
+    }
+
+    let sale_rows = repository::get_by_registrator(&sale.id).await?;
+    if sale_rows.is_empty() {
+        return Ok(None);
+    }
+
+    let ratio = return_ratio(document.line.qty, sale.qty);
+    let return_date = wb_business_date_str(&document.state.sale_dt);
+    Ok(Some(reverse_sale_rows(
+        &sale_rows,
+        document_id,
+        &return_date,
+        mode,
+        ratio,
+    )))
+}
+
+/// Доля продажи, которую отменяет возврат: |qty возврата| / qty продажи, не больше 1.
+fn return_ratio(return_qty: f64, sale_qty: f64) -> f64 {
+    if sale_qty <= 0.0 || return_qty == 0.0 {
+        return 1.0;
+    }
+    (return_qty.abs() / sale_qty).min(1.0)
+}
+
+/// Сторно строк продажи от имени документа возврата.
+fn reverse_sale_rows(
+    sale_rows: &[Model],
+    return_id: &str,
+    return_date: &str,
+    mode: ReturnNettingMode,
+    ratio: f64,
+) -> Vec<Model> {
+    let now = Utc::now().to_rfc3339();
+    let k = -ratio;
+    sale_rows
+        .iter()
+        .map(|sale| Model {
+            id: Uuid::new_v4().to_string(),
+            registrator_ref: return_id.to_string(),
+            registrator_type: sale.registrator_type.clone(),
+            date: match mode {
+                ReturnNettingMode::SaleDate => sale.date.clone(),
+                _ => return_date.to_string(),
+            },
+            connection_mp_ref: sale.connection_mp_ref.clone(),
+            nomenclature_ref: sale.nomenclature_ref.clone(),
+            marketplace_product_ref: sale.marketplace_product_ref.clone(),
+            // Выручка продажи уходит в customer_out со знаком минус — как у обычного возврата.
+            customer_in: 0.0,
+            customer_out: (sale.customer_in + sale.customer_out) * k,
+            coinvest_in: sale.coinvest_in * k,
+            commission_out: sale.commission_out * k,
+            acquiring_out: sale.acquiring_out * k,
+            penalty_out: sale.penalty_out * k,
+            logistics_out: sale.logistics_out * k,
+            seller_out: sale.seller_out * k,
+            price_full: sale.price_full * k,
+            price_list: sale.price_list * k,
+            price_return: sale.price_list * ratio,
+            commission_percent: sale.commission_percent,
+            coinvest_persent: sale.coinvest_persent,
+            total: sale.total * k,
+            cost: sale.cost.map(|cost| cost * k),
+            document_no: sale.document_no.clone(),
+            article: sale.article.clone(),
+            posted_at: now.clone(),
+        })
+        .collect()
+}
+
+/// Проведённые возвраты за период без проведённой продажи.
+pub async fn list_unmatched(date_from: &str, date_to: &str) -> Result<UnmatchedReturnsResponse> {
+    let rows = crate::domain::a012_wb_sales::repository::list_unmatched_returns(date_from, date_to)
+        .await?;
+    let items: Vec<UnmatchedReturnDto> = rows
+        .into_iter()
+        .map(|row| UnmatchedReturnDto {
+            id: row.id,
+            document_no: row.document_no,
+            sale_date: row.sale_date,
+            supplier_article: row.supplier_article,
+            connection_mp_ref: row.connection_id,
+            finished_price: row.finished_price,
+            reason: if row.sale_exists {
+                "sale_not_posted".to_string()
+            } else {
+                "sale_not_found".to_string()
+            },
+        })
+        .collect();
+    let total_count = items.len() as i32;
+    Ok(UnmatchedReturnsResponse { items, total_count })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sale_row() -> Model {
+        Model {
+            id: "sale-row".to_string(),
+            registrator_ref: "sale-doc".to_string(),
+            registrator_type: "WB_Sales".to_string(),
+            date: "2026-03-01".to_string(),
+            connection_mp_ref: "cab".to_string(),
+            nomenclature_ref: "nom".to_string(),
+            marketplace_product_ref: "mp".to_string(),
+            customer_in: 1000.0,
+            customer_out: 0.0,
+            coinvest_in: 50.0,
+            commission_out: -200.0,
+            acquiring_out: -19.0,
+            penalty_out: 0.0,
+            logistics_out: 0.0,
+            seller_out: -831.0,
+            price_full: 1500.0,
+            price_list: 1200.0,
+            price_return: 0.0,
+            commission_percent: 20.0,
+            coinvest_persent: 5.0,
+            total: 831.0,
+            cost: Some(-400.0),
+            document_no: "srid-1".to_string(),
+            article: "ART".to_string(),
+            posted_at: "2026-03-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn reversal_cancels_sale_totals() {
+        let sale = sale_row();
+        let rows = reverse_sale_rows(
+            std::slice::from_ref(&sale),
+            "return-doc",
+            "2026-03-10",
+            ReturnNettingMode::ReturnDate,
+            1.0,
+        );
+
+        let r = &rows[0];
+        assert_eq!(r.registrator_ref, "return-doc");
+        assert_eq!(r.date, "2026-03-10");
+        assert_eq!(sale.customer_in + r.customer_out, 0.0);
+        assert_eq!(sale.commission_out + r.commission_out, 0.0);
+        assert_eq!(sale.acquiring_out + r.acquiring_out, 0.0);
+        assert_eq!(sale.total + r.total, 0.0);
+        assert_eq!(r.cost, Some(400.0));
+        assert_eq!(r.price_return, 1200.0);
+    }
+
+    #[test]
+    fn sale_date_mode_keeps_sale_day_and_ratio_scales() {
+        let rows = reverse_sale_rows(
+            &[sale_row()],
+            "return-doc",
+            "2026-03-10",
+            ReturnNettingMode::SaleDate,
+            0.5,
+        );
+        assert_eq!(rows[0].date, "2026-03-01");
+        assert_eq!(rows[0].customer_out, -500.0);
+        assert_eq!(rows[0].commission_out, 100.0);
+    }
+
+    #[test]
+    fn return_ratio_is_capped_and_defaults_to_full() {
+        assert_eq!(return_ratio(-1.0, 2.0), 0.5);
+        assert_eq!(return_ratio(-3.0, 2.0), 1.0);
+        assert_eq!(return_ratio(-1.0, 0.0), 1.0);
+        assert_eq!(return_ratio(0.0, 1.0), 1.0);
+    }
+}
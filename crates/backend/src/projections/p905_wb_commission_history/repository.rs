@@ -7,6 +7,10 @@ use serde::{Deserialize, Serialize};
 use crate::shared::data::db::get_connection;
 
 /// Модель Wildberries Commission History entry
+///
+/// Строка — снимок общего тарифа WB на дату, загруженный импортом u504, а не
+/// движение документа: регистратора и ссылки на документ-источник у неё нет.
+/// Происхождение строки — `raw_json` ответа API и `loaded_at_utc`.
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "p905_wb_commission_history")]
 pub struct Model {
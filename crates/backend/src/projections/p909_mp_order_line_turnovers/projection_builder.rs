@@ -842,8 +842,10 @@ mod tests {
                 raw_payload_ref: "raw-1".to_string(),
                 fetched_at: sale_dt,
                 document_version: 1,
+                import_session_id: None,
             },
             is_posted: true,
+            is_sandbox: false,
             is_customer_return: is_return,
             marketplace_product_ref: Some("mp-prod-1".to_string()),
             nomenclature_ref: Some("nom-1".to_string()),
@@ -133,6 +133,10 @@ pub async fn get_by_id(id: &str) -> Result<Option<Model>> {
 }
 
 pub async fn save_entry(entry: &Model) -> Result<()> {
+    save_entry_with_conn(conn(), entry).await
+}
+
+pub async fn save_entry_with_conn<C: ConnectionTrait>(db: &C, entry: &Model) -> Result<()> {
     let active = ActiveModel {
         id: Set(entry.id.clone()),
         connection_mp_ref: Set(entry.connection_mp_ref.clone()),
@@ -157,16 +161,17 @@ pub async fn save_entry(entry: &Model) -> Result<()> {
     };
 
     if Entity::find_by_id(entry.id.clone())
-        .one(conn())
+        .one(db)
         .await?
         .is_some()
     {
-        active.update(conn()).await?;
+        active.update(db).await?;
     } else {
-        active.insert(conn()).await?;
+        active.insert(db).await?;
     }
 
-    update_group_link_status(
+    update_group_link_status_with_conn(
+        db,
         &entry.connection_mp_ref,
         &entry.line_event_key,
         &entry.turnover_code,
@@ -294,25 +299,47 @@ pub async fn count_with_filters(
 pub async fn list_by_connection_and_line_key(
     connection_mp_ref: &str,
     line_key: &str,
+) -> Result<Vec<Model>> {
+    list_by_connection_and_line_key_with_conn(conn(), connection_mp_ref, line_key).await
+}
+
+pub async fn list_by_connection_and_line_key_with_conn<C: ConnectionTrait>(
+    db: &C,
+    connection_mp_ref: &str,
+    line_key: &str,
 ) -> Result<Vec<Model>> {
     Ok(Entity::find()
         .filter(Column::ConnectionMpRef.eq(connection_mp_ref))
         .filter(Column::LineKey.eq(line_key))
-        .all(conn())
+        .all(db)
         .await?)
 }
 
 pub async fn list_by_registrator_ref(registrator_ref: &str) -> Result<Vec<Model>> {
+    list_by_registrator_ref_with_conn(conn(), registrator_ref).await
+}
+
+pub async fn list_by_registrator_ref_with_conn<C: ConnectionTrait>(
+    db: &C,
+    registrator_ref: &str,
+) -> Result<Vec<Model>> {
     Ok(Entity::find()
         .filter(Column::RegistratorRef.eq(registrator_ref))
-        .all(conn())
+        .all(db)
         .await?)
 }
 
 pub async fn list_link_groups_by_registrator_ref(
     registrator_ref: &str,
 ) -> Result<Vec<LinkGroupKey>> {
-    let rows = list_by_registrator_ref(registrator_ref).await?;
+    list_link_groups_by_registrator_ref_with_conn(conn(), registrator_ref).await
+}
+
+pub async fn list_link_groups_by_registrator_ref_with_conn<C: ConnectionTrait>(
+    db: &C,
+    registrator_ref: &str,
+) -> Result<Vec<LinkGroupKey>> {
+    let rows = list_by_registrator_ref_with_conn(db, registrator_ref).await?;
     let groups: BTreeSet<LinkGroupKey> = rows
         .into_iter()
         .map(|row| LinkGroupKey {
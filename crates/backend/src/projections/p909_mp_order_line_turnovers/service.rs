@@ -1,8 +1,10 @@
 use anyhow::Result;
 use chrono::NaiveDate;
+use sea_orm::ConnectionTrait;
 use uuid::Uuid;
 
 use super::{projection_builder, repository};
+use crate::shared::data::db::get_connection;
 
 pub async fn get_by_id(id: &str) -> Result<Option<repository::Model>> {
     repository::get_by_id(id).await
@@ -64,23 +66,27 @@ pub async fn count_with_filters(
     .await
 }
 
-pub async fn project_wb_order(
+/// Строки заказа и привязка к нему уже существующих строк той же позиции
+/// (в транзакции вызывающего — см. `unit_of_work`).
+pub async fn project_wb_order_with_conn<C: ConnectionTrait>(
+    db: &C,
     document: &contracts::domain::a015_wb_orders::aggregate::WbOrders,
     document_id: Uuid,
 ) -> Result<()> {
     let document_id_str = document_id.to_string();
     for entry in projection_builder::from_wb_order(document, &document_id_str)? {
-        repository::upsert_entry(&entry).await?;
+        repository::save_entry_with_conn(db, &entry).await?;
     }
 
-    let related = repository::list_by_connection_and_line_key(
+    let related = repository::list_by_connection_and_line_key_with_conn(
+        db,
         &document.header.connection_id,
         &document.line.line_id,
     )
     .await?;
     for mut entry in related {
         projection_builder::attach_order_context(&mut entry, document, &document_id_str);
-        repository::save_entry(&entry).await?;
+        repository::save_entry_with_conn(db, &entry).await?;
     }
 
     Ok(())
@@ -124,15 +130,24 @@ pub async fn project_wb_finance_entry(
 }
 
 pub async fn remove_by_registrator_ref(registrator_ref: &str) -> Result<()> {
-    let affected_groups = repository::list_link_groups_by_registrator_ref(registrator_ref).await?;
+    remove_by_registrator_ref_with_conn(get_connection(), registrator_ref).await
+}
+
+pub async fn remove_by_registrator_ref_with_conn<C: ConnectionTrait>(
+    db: &C,
+    registrator_ref: &str,
+) -> Result<()> {
+    let affected_groups =
+        repository::list_link_groups_by_registrator_ref_with_conn(db, registrator_ref).await?;
     if affected_groups.is_empty() {
         return Ok(());
     }
 
-    repository::delete_many_by_registrator_ref(registrator_ref).await?;
+    repository::delete_many_by_registrator_ref_with_conn(db, registrator_ref).await?;
 
     for group in affected_groups {
-        repository::refresh_group_link_status(
+        repository::refresh_group_link_status_with_conn(
+            db,
             &group.connection_mp_ref,
             &group.line_event_key,
             &group.turnover_code,
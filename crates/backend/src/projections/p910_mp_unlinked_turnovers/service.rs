@@ -59,15 +59,30 @@ pub async fn project_wb_finance_entry(
     posting_id: &str,
 ) -> Result<()> {
     let result = projection_builder::from_wb_finance_row(entry, posting_id)?;
-    for model in result.turnovers {
-        repository::upsert_entry(&model).await?;
+    for model in &result.turnovers {
+        repository::upsert_entry(model).await?;
     }
     crate::general_ledger::service::save_entries(&result.general_ledger_entries).await?;
+    // Доли хранения по SKU — вспомогательная проекция: её сбой не отменяет проведение,
+    // период можно перераспределить позже.
+    if let Err(e) =
+        crate::projections::p918_storage_cost_allocation::service::allocate_wb_finance_entry(
+            entry,
+            &result.turnovers,
+        )
+        .await
+    {
+        tracing::warn!("p918 allocation failed for {}: {}", entry.id, e);
+    }
     Ok(())
 }
 
 pub async fn remove_by_registrator_ref(registrator_ref: &str) -> Result<()> {
     repository::delete_by_registrator_ref(registrator_ref).await?;
+    crate::projections::p918_storage_cost_allocation::service::remove_by_registrator_ref(
+        registrator_ref,
+    )
+    .await?;
     Ok(())
 }
 
@@ -66,16 +66,6 @@ pub async fn insert_entry_raw_with_conn<C: ConnectionTrait>(db: &C, entry: &Mode
     Ok(())
 }
 
-/// Удаление всех событий по ссылке регистратора (autocommit). Используется
-/// при распроведении a013 (где нет внешней транзакции).
-pub async fn delete_by_registrator_ref(registrator_ref: &str) -> Result<u64> {
-    let result = Entity::delete_many()
-        .filter(Column::RegistratorRef.eq(registrator_ref))
-        .exec(conn())
-        .await?;
-    Ok(result.rows_affected)
-}
-
 /// Удаление событий по набору ссылок регистраторов в рамках транзакции.
 /// Используется в пути перепроведения p907.
 pub async fn delete_by_registrator_refs_with_conn<C: ConnectionTrait>(
@@ -428,6 +428,7 @@ mod tests {
             marketplace_raw_payload_ref: None,
             fetched_at: utc("2026-03-01T06:00:00Z"),
             document_version: 1,
+            import_session_id: None,
         };
         let mut doc = WbOrders::new_for_insert(
             "code-1".to_string(),
@@ -663,6 +664,7 @@ mod tests {
             raw_payload_ref: "raw-9".to_string(),
             fetched_at: utc("2026-03-10T06:00:00Z"),
             document_version: 1,
+            import_session_id: None,
         };
         let mut doc = WbSales::new_for_insert(
             "code-9".to_string(),
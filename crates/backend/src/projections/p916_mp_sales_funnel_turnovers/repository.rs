@@ -174,16 +174,6 @@ pub async fn insert_many_with_conn<C: ConnectionTrait>(db: &C, entries: &[Model]
     Ok(())
 }
 
-/// Удаление строк по ссылке регистратора (autocommit). Используется при
-/// распроведении, где нет внешней транзакции.
-pub async fn delete_by_registrator_ref(registrator_ref: &str) -> Result<u64> {
-    let result = Entity::delete_many()
-        .filter(Column::RegistratorRef.eq(registrator_ref))
-        .exec(conn())
-        .await?;
-    Ok(result.rows_affected)
-}
-
 /// Все строки движения конкретного регистратора (тип + ссылка). Используется закладкой
 /// «Проекции» документа-источника — показывает ровно те движения воронки, что он породил.
 pub async fn list_by_registrator(
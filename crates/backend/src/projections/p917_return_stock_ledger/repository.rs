@@ -0,0 +1,96 @@
+//! Репозиторий проекции `p917_return_stock_ledger` (движения возвращённого товара).
+//!
+//! Регистратор — решение по возврату (`sys_return_dispositions`): при утверждении
+//! его строки удаляются и вставляются заново в одной транзакции.
+
+use anyhow::Result;
+use contracts::projections::p917_return_stock_ledger::dto::ReturnStockLedgerEntryDto;
+use sea_orm::entity::prelude::*;
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "p917_return_stock_ledger")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub entry_date: String,
+    pub decision: String,
+    pub entity_type: String,
+    pub document_id: String,
+    pub line_no: i32,
+    pub organization_id: String,
+    pub article: String,
+    pub product_name: String,
+    pub quantity: i32,
+    pub stock_delta: i32,
+    pub registrator_ref: String,
+    pub created_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for ReturnStockLedgerEntryDto {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            entry_date: model.entry_date,
+            decision: model.decision,
+            entity_type: model.entity_type,
+            document_id: model.document_id,
+            line_no: model.line_no,
+            organization_id: model.organization_id,
+            article: model.article,
+            product_name: model.product_name,
+            quantity: model.quantity,
+            stock_delta: model.stock_delta,
+            registrator_ref: model.registrator_ref,
+            created_at: model.created_at,
+        }
+    }
+}
+
+/// Заменяет движения регистратора: удаляет прежние и вставляет `entries`.
+pub async fn replace_for_registrator_with_conn<C: ConnectionTrait>(
+    db: &C,
+    registrator_ref: &str,
+    entries: Vec<Model>,
+) -> Result<()> {
+    Entity::delete_many()
+        .filter(Column::RegistratorRef.eq(registrator_ref))
+        .exec(db)
+        .await?;
+    for entry in entries {
+        ActiveModel {
+            id: Set(entry.id),
+            entry_date: Set(entry.entry_date),
+            decision: Set(entry.decision),
+            entity_type: Set(entry.entity_type),
+            document_id: Set(entry.document_id),
+            line_no: Set(entry.line_no),
+            organization_id: Set(entry.organization_id),
+            article: Set(entry.article),
+            product_name: Set(entry.product_name),
+            quantity: Set(entry.quantity),
+            stock_delta: Set(entry.stock_delta),
+            registrator_ref: Set(entry.registrator_ref),
+            created_at: Set(entry.created_at),
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Движения регистратора по порядку строк документа.
+pub async fn list_by_registrator(registrator_ref: &str) -> Result<Vec<Model>> {
+    Ok(Entity::find()
+        .filter(Column::RegistratorRef.eq(registrator_ref))
+        .order_by_asc(Column::LineNo)
+        .all(get_connection())
+        .await?)
+}
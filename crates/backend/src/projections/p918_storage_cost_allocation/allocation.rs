@@ -0,0 +1,71 @@
+//! Пропорциональное деление суммы расхода по весам драйвера.
+
+/// Доля и сумма одного получателя.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Split {
+    pub share: f64,
+    pub amount: f64,
+}
+
+fn round_kopecks(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Делит `amount` пропорционально `weights` с точностью до копейки: остаток
+/// округления уходит получателю с наибольшим весом, и сумма долей в точности
+/// равна `amount`. Неположительные веса получают ноль. `None` — делить не на что.
+pub fn split_amount(amount: f64, weights: &[f64]) -> Option<Vec<Split>> {
+    let total: f64 = weights.iter().filter(|w| **w > 0.0).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut splits: Vec<Split> = weights
+        .iter()
+        .map(|w| {
+            let share = if *w > 0.0 { w / total } else { 0.0 };
+            Split {
+                share,
+                amount: round_kopecks(amount * share),
+            }
+        })
+        .collect();
+    let allocated: f64 = splits.iter().map(|s| s.amount).sum();
+    let remainder = round_kopecks(amount - allocated);
+    if remainder != 0.0 {
+        let largest = weights
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(index, _)| index)?;
+        splits[largest].amount = round_kopecks(splits[largest].amount + remainder);
+    }
+    Some(splits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_is_proportional_and_sums_to_amount() {
+        let splits = split_amount(-100.0, &[2.0, 1.0, 1.0, 2.0, 3.0]).unwrap();
+        let total: f64 = splits.iter().map(|s| s.amount).sum();
+        assert!((total + 100.0).abs() < 1e-9);
+        assert_eq!(splits[0].amount, -22.22);
+        assert_eq!(splits[1].amount, -11.11);
+        // -33.33 + остаток -0.01 — у наибольшего веса
+        assert_eq!(splits[4].amount, -33.34);
+        assert!((splits[4].share - 1.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn zero_weights_get_nothing_and_empty_basis_is_none() {
+        let splits = split_amount(50.0, &[0.0, 3.0, 1.0]).unwrap();
+        assert_eq!(splits[0].amount, 0.0);
+        assert_eq!(splits[1].amount, 37.5);
+        assert_eq!(splits[2].amount, 12.5);
+
+        assert!(split_amount(50.0, &[0.0, 0.0]).is_none());
+        assert!(split_amount(50.0, &[]).is_none());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod allocation;
+pub mod repository;
+pub mod service;
@@ -0,0 +1,225 @@
+//! Репозиторий проекции `p918_storage_cost_allocation` (доли хранения и приёмки по SKU).
+//!
+//! Источник — строка расхода p910: при каждом распределении её строки удаляются
+//! и вставляются заново в одной транзакции.
+
+use anyhow::Result;
+use contracts::projections::p918_storage_cost_allocation::dto::StorageCostAllocationDto;
+use sea_orm::entity::prelude::*;
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, Statement, TransactionTrait,
+};
+use std::collections::HashMap;
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "p918_storage_cost_allocation")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub source_ref: String,
+    pub registrator_ref: String,
+    pub connection_mp_ref: String,
+    pub entry_date: String,
+    pub turnover_code: String,
+    pub source_amount: f64,
+    pub driver: String,
+    pub basis: String,
+    #[sea_orm(nullable)]
+    pub nm_id: Option<i64>,
+    pub article: String,
+    #[sea_orm(nullable)]
+    pub nomenclature_ref: Option<String>,
+    pub driver_value: f64,
+    pub driver_total: f64,
+    pub share: f64,
+    pub amount: f64,
+    #[sea_orm(nullable)]
+    pub comment: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for StorageCostAllocationDto {
+    fn from(model: Model) -> Self {
+        Self {
+            id: model.id,
+            source_ref: model.source_ref,
+            registrator_ref: model.registrator_ref,
+            connection_mp_ref: model.connection_mp_ref,
+            entry_date: model.entry_date,
+            turnover_code: model.turnover_code,
+            source_amount: model.source_amount,
+            driver: model.driver,
+            basis: model.basis,
+            nm_id: model.nm_id,
+            article: model.article,
+            nomenclature_ref: model.nomenclature_ref,
+            driver_value: model.driver_value,
+            driver_total: model.driver_total,
+            share: model.share,
+            amount: model.amount,
+            comment: model.comment,
+            created_at: model.created_at,
+        }
+    }
+}
+
+/// Заменяет доли строки расхода: удаляет прежние и вставляет `rows`.
+pub async fn replace_for_source(source_ref: &str, rows: Vec<Model>) -> Result<()> {
+    let txn = get_connection().begin().await?;
+    Entity::delete_many()
+        .filter(Column::SourceRef.eq(source_ref))
+        .exec(&txn)
+        .await?;
+    for row in rows {
+        ActiveModel {
+            id: Set(row.id),
+            source_ref: Set(row.source_ref),
+            registrator_ref: Set(row.registrator_ref),
+            connection_mp_ref: Set(row.connection_mp_ref),
+            entry_date: Set(row.entry_date),
+            turnover_code: Set(row.turnover_code),
+            source_amount: Set(row.source_amount),
+            driver: Set(row.driver),
+            basis: Set(row.basis),
+            nm_id: Set(row.nm_id),
+            article: Set(row.article),
+            nomenclature_ref: Set(row.nomenclature_ref),
+            driver_value: Set(row.driver_value),
+            driver_total: Set(row.driver_total),
+            share: Set(row.share),
+            amount: Set(row.amount),
+            comment: Set(row.comment),
+            created_at: Set(row.created_at),
+        }
+        .insert(&txn)
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+/// Удаляет доли всех расходов строки финансового отчёта (при отмене проведения).
+pub async fn delete_by_registrator_ref(registrator_ref: &str) -> Result<u64> {
+    let result = Entity::delete_many()
+        .filter(Column::RegistratorRef.eq(registrator_ref))
+        .exec(get_connection())
+        .await?;
+    Ok(result.rows_affected)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AllocationListQuery {
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub connection_mp_ref: Option<String>,
+    pub turnover_code: Option<String>,
+    pub nm_id: Option<i64>,
+    pub source_ref: Option<String>,
+    /// Только нераспределённые остатки
+    pub unallocated_only: bool,
+    pub offset: u64,
+    pub limit: u64,
+}
+
+fn list_condition(query: &AllocationListQuery) -> Condition {
+    let mut condition = Condition::all();
+    if let Some(value) = query.date_from.as_deref().filter(|v| !v.is_empty()) {
+        condition = condition.add(Column::EntryDate.gte(value));
+    }
+    if let Some(value) = query.date_to.as_deref().filter(|v| !v.is_empty()) {
+        condition = condition.add(Column::EntryDate.lte(value));
+    }
+    if let Some(value) = query.connection_mp_ref.as_deref().filter(|v| !v.is_empty()) {
+        condition = condition.add(Column::ConnectionMpRef.eq(value));
+    }
+    if let Some(value) = query.turnover_code.as_deref().filter(|v| !v.is_empty()) {
+        condition = condition.add(Column::TurnoverCode.eq(value));
+    }
+    if let Some(value) = query.nm_id {
+        condition = condition.add(Column::NmId.eq(value));
+    }
+    if let Some(value) = query.source_ref.as_deref().filter(|v| !v.is_empty()) {
+        condition = condition.add(Column::SourceRef.eq(value));
+    }
+    if query.unallocated_only {
+        condition = condition.add(Column::NmId.is_null());
+    }
+    condition
+}
+
+pub async fn list(query: &AllocationListQuery) -> Result<(Vec<Model>, u64)> {
+    let db = get_connection();
+    let total = Entity::find()
+        .filter(list_condition(query))
+        .count(db)
+        .await?;
+    let items = Entity::find()
+        .filter(list_condition(query))
+        .order_by_desc(Column::EntryDate)
+        .order_by_asc(Column::SourceRef)
+        .order_by_desc(Column::Amount)
+        .offset(query.offset)
+        .limit(query.limit)
+        .all(db)
+        .await?;
+    Ok((items, total))
+}
+
+/// Распределённые суммы по nmID за период (для маржи D409).
+pub async fn amounts_by_nm(
+    nm_ids: &[i64],
+    date_from: &str,
+    date_to: &str,
+    connection_mp_ref: Option<&str>,
+    organization_ref: Option<&str>,
+) -> Result<HashMap<i64, f64>> {
+    if nm_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let db = get_connection();
+    let placeholders = vec!["?"; nm_ids.len()].join(", ");
+    let mut values: Vec<sea_orm::Value> = nm_ids.iter().map(|id| (*id).into()).collect();
+    values.push(date_from.into());
+    values.push(date_to.into());
+    let mut sql = format!(
+        "SELECT nm_id, SUM(amount) AS amount \
+         FROM p918_storage_cost_allocation \
+         WHERE nm_id IN ({placeholders}) AND entry_date >= ? AND entry_date <= ?"
+    );
+    if let Some(connection) = connection_mp_ref {
+        sql.push_str(" AND connection_mp_ref = ?");
+        values.push(connection.into());
+    }
+    // Организации в строке нет — она берётся из кабинета маркетплейса
+    if let Some(organization) = organization_ref {
+        sql.push_str(
+            " AND connection_mp_ref IN (SELECT id FROM a006_connection_mp \
+             WHERE LOWER(TRIM(REPLACE(COALESCE(organization_ref, ''), '\"', ''))) = LOWER(TRIM(REPLACE(?, '\"', ''))))",
+        );
+        values.push(organization.into());
+    }
+    sql.push_str(" GROUP BY nm_id");
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            sql,
+            values,
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let nm_id = row.try_get::<i64>("", "nm_id").ok()?;
+            let amount = row.try_get::<f64>("", "amount").ok()?;
+            Some((nm_id, amount))
+        })
+        .collect())
+}
@@ -0,0 +1,318 @@
+//! Распределение расходов на хранение и приёмку WB по SKU.
+//!
+//! Строка расхода p910 (`mp_storage`, `acceptance`) без товара делится между
+//! nmID по драйверу из правил:
+//! - «штук на хранении» — остаток WB из снимка товаров a037 на дату расхода
+//!   (последний снимок не позже неё);
+//! - «доля объёма» — тот же остаток × объём единицы из габаритов карточки;
+//! - «доля выручки» — продажи финансового отчёта за `REVENUE_WINDOW_DAYS`.
+//!
+//! Если в строке отчёта WB товар уже указан, расход целиком относится на него.
+//! Когда делить не на что (нет снимка, габаритов или продаж), сумма остаётся
+//! одной нераспределённой строкой — итог p918 всегда равен итогу расходов p910.
+
+use anyhow::Result;
+use chrono::{Duration, NaiveDate, Utc};
+use contracts::projections::p918_storage_cost_allocation::dto::{
+    AllocationDriver, StorageAllocationRebuildResponse, StorageAllocationSettings,
+    ALLOCATED_TURNOVER_CODES, DRIVER_DIRECT, REVENUE_WINDOW_DAYS,
+};
+use sea_orm::{ConnectionTrait, Statement};
+use std::collections::HashMap;
+
+use super::allocation::split_amount;
+use super::repository::{self, Model};
+use crate::projections::p903_wb_finance_report::repository as p903;
+use crate::projections::p910_mp_unlinked_turnovers::repository as p910;
+use crate::shared::data::db::get_connection;
+
+/// Вес SKU по драйверу.
+#[derive(Debug, Clone)]
+struct DriverWeight {
+    nm_id: i64,
+    article: String,
+    nomenclature_ref: Option<String>,
+    value: f64,
+}
+
+/// База драйвера: описание (дата снимка, период) и веса SKU.
+#[derive(Debug, Clone, Default)]
+struct DriverBasis {
+    basis: String,
+    weights: Vec<DriverWeight>,
+}
+
+/// Базы драйверов, уже посчитанные в этом прогоне: (драйвер, кабинет, дата).
+#[derive(Default)]
+struct BasisCache {
+    settings: StorageAllocationSettings,
+    bases: HashMap<(AllocationDriver, String, String), DriverBasis>,
+}
+
+impl BasisCache {
+    async fn load() -> Result<Self> {
+        Ok(Self {
+            settings: crate::system::settings::service::get_p918_storage_allocation_settings()
+                .await?,
+            bases: HashMap::new(),
+        })
+    }
+
+    async fn basis(
+        &mut self,
+        driver: AllocationDriver,
+        connection_mp_ref: &str,
+        date: &str,
+    ) -> Result<&DriverBasis> {
+        let key = (driver, connection_mp_ref.to_string(), date.to_string());
+        if !self.bases.contains_key(&key) {
+            let basis = match driver {
+                AllocationDriver::UnitsStored => {
+                    stock_basis(connection_mp_ref, date, false).await?
+                }
+                AllocationDriver::VolumeShare => stock_basis(connection_mp_ref, date, true).await?,
+                AllocationDriver::RevenueShare => revenue_basis(connection_mp_ref, date).await?,
+            };
+            self.bases.insert(key.clone(), basis);
+        }
+        Ok(&self.bases[&key])
+    }
+}
+
+/// Остатки WB по снимку a037; для доли объёма — в литрах, SKU без габаритов пропускаются.
+async fn stock_basis(connection_mp_ref: &str, date: &str, by_volume: bool) -> Result<DriverBasis> {
+    let Some(snapshot) = crate::domain::a037_wb_product_snapshot::repository::latest_on_or_before(
+        connection_mp_ref,
+        Some(date),
+    )
+    .await?
+    else {
+        return Ok(DriverBasis::default());
+    };
+    let volumes = if by_volume {
+        crate::domain::a007_marketplace_product::dimensions::volumes_by_sku(connection_mp_ref)
+            .await?
+    } else {
+        HashMap::new()
+    };
+
+    let mut without_volume = 0;
+    let mut weights = Vec::new();
+    for line in snapshot.lines.into_iter().filter(|l| l.state.stock_wb > 0) {
+        let units = line.state.stock_wb as f64;
+        let value = if by_volume {
+            match volumes.get(&line.nm_id.to_string()) {
+                Some(volume) => units * volume,
+                None => {
+                    without_volume += 1;
+                    continue;
+                }
+            }
+        } else {
+            units
+        };
+        weights.push(DriverWeight {
+            nm_id: line.nm_id,
+            article: line.vendor_code,
+            nomenclature_ref: line.nomenclature_ref,
+            value,
+        });
+    }
+
+    let mut basis = format!("Снимок остатков a037 от {}", snapshot.header.snapshot_date);
+    if without_volume > 0 {
+        basis.push_str(&format!("; без габаритов: {} SKU", without_volume));
+    }
+    Ok(DriverBasis { basis, weights })
+}
+
+/// Выручка продаж p903 по nmID за `REVENUE_WINDOW_DAYS`, заканчивая `date`.
+async fn revenue_basis(connection_mp_ref: &str, date: &str) -> Result<DriverBasis> {
+    let Ok(date_to) = NaiveDate::parse_from_str(date, "%Y-%m-%d") else {
+        return Ok(DriverBasis::default());
+    };
+    let date_from = date_to - Duration::days(REVENUE_WINDOW_DAYS - 1);
+    let db = get_connection();
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "SELECT r.nm_id AS nm_id, \
+                    COALESCE(MAX(r.sa_name), '') AS article, \
+                    MAX(mp.nomenclature_ref) AS nomenclature_ref, \
+                    SUM(COALESCE(r.retail_amount, 0)) AS revenue \
+             FROM p903_wb_finance_report r \
+             LEFT JOIN a007_marketplace_product mp \
+                    ON mp.connection_mp_ref = r.connection_mp_ref \
+                   AND mp.marketplace_sku = CAST(r.nm_id AS TEXT) \
+                   AND mp.is_deleted = 0 \
+             WHERE r.connection_mp_ref = ? AND r.rr_dt >= ? AND r.rr_dt <= ? \
+               AND r.supplier_oper_name = 'Продажа' AND r.nm_id > 0 \
+             GROUP BY r.nm_id",
+            [
+                connection_mp_ref.into(),
+                date_from.format("%Y-%m-%d").to_string().into(),
+                date.into(),
+            ],
+        ))
+        .await?;
+    let weights = rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(DriverWeight {
+                nm_id: row.try_get::<i64>("", "nm_id").ok()?,
+                article: row.try_get("", "article").unwrap_or_default(),
+                nomenclature_ref: row.try_get("", "nomenclature_ref").ok().flatten(),
+                value: row.try_get::<f64>("", "revenue").ok()?,
+            })
+        })
+        .collect();
+    Ok(DriverBasis {
+        basis: format!(
+            "Выручка p903 за {} — {}",
+            date_from.format("%Y-%m-%d"),
+            date
+        ),
+        weights,
+    })
+}
+
+fn allocation_row(source: &p910::Model, driver: &str, basis: &str, created_at: &str) -> Model {
+    Model {
+        id: format!("{}:unallocated", source.id),
+        source_ref: source.id.clone(),
+        registrator_ref: source.registrator_ref.clone(),
+        connection_mp_ref: source.connection_mp_ref.clone(),
+        entry_date: source.entry_date.clone(),
+        turnover_code: source.turnover_code.clone(),
+        source_amount: source.amount,
+        driver: driver.to_string(),
+        basis: basis.to_string(),
+        nm_id: None,
+        article: String::new(),
+        nomenclature_ref: None,
+        driver_value: 0.0,
+        driver_total: 0.0,
+        share: 1.0,
+        amount: source.amount,
+        comment: None,
+        created_at: created_at.to_string(),
+    }
+}
+
+/// Строки p918 для одной строки расхода; `finance_row` — её строка отчёта WB.
+/// Возвращает строки и признак нераспределённого остатка.
+async fn allocate_row(
+    cache: &mut BasisCache,
+    source: &p910::Model,
+    finance_row: Option<&p903::Model>,
+) -> Result<(Vec<Model>, bool)> {
+    let created_at = Utc::now().to_rfc3339();
+
+    if let Some(nm_id) = finance_row.and_then(|r| r.nm_id).filter(|id| *id > 0) {
+        let mut row = allocation_row(
+            source,
+            DRIVER_DIRECT,
+            "Товар указан в отчёте WB",
+            &created_at,
+        );
+        row.id = format!("{}:{}", source.id, nm_id);
+        row.nm_id = Some(nm_id);
+        row.article = finance_row
+            .and_then(|r| r.sa_name.clone())
+            .unwrap_or_default();
+        return Ok((vec![row], false));
+    }
+
+    let driver = cache
+        .settings
+        .driver_for(&source.connection_mp_ref, &source.turnover_code);
+    let basis = cache
+        .basis(driver, &source.connection_mp_ref, &source.entry_date)
+        .await?;
+    let values: Vec<f64> = basis.weights.iter().map(|w| w.value).collect();
+    let Some(splits) = split_amount(source.amount, &values) else {
+        let mut row = allocation_row(source, driver.code(), &basis.basis, &created_at);
+        row.comment = Some(format!("Нет данных драйвера «{}»", driver.label()));
+        return Ok((vec![row], true));
+    };
+
+    let driver_total: f64 = values.iter().filter(|v| **v > 0.0).sum();
+    let rows = basis
+        .weights
+        .iter()
+        .zip(splits)
+        .filter(|(_, split)| split.amount != 0.0)
+        .map(|(weight, split)| {
+            let mut row = allocation_row(source, driver.code(), &basis.basis, &created_at);
+            row.id = format!("{}:{}", source.id, weight.nm_id);
+            row.nm_id = Some(weight.nm_id);
+            row.article = weight.article.clone();
+            row.nomenclature_ref = weight.nomenclature_ref.clone();
+            row.driver_value = weight.value;
+            row.driver_total = driver_total;
+            row.share = split.share;
+            row.amount = split.amount;
+            row
+        })
+        .collect();
+    Ok((rows, false))
+}
+
+fn is_allocated(source: &p910::Model) -> bool {
+    source.registrator_type == "p903_wb_finance_report"
+        && source.nomenclature_ref.is_none()
+        && ALLOCATED_TURNOVER_CODES.contains(&source.turnover_code.as_str())
+}
+
+/// Распределяет расходы, спроецированные из строки отчёта WB (вызывается из p910).
+pub async fn allocate_wb_finance_entry(
+    finance_row: &p903::Model,
+    turnovers: &[p910::Model],
+) -> Result<()> {
+    let mut cache = BasisCache::load().await?;
+    for source in turnovers.iter().filter(|t| is_allocated(t)) {
+        let (rows, _) = allocate_row(&mut cache, source, Some(finance_row)).await?;
+        repository::replace_for_source(&source.id, rows).await?;
+    }
+    Ok(())
+}
+
+pub async fn remove_by_registrator_ref(registrator_ref: &str) -> Result<()> {
+    repository::delete_by_registrator_ref(registrator_ref).await?;
+    Ok(())
+}
+
+/// Перераспределяет расходы периода по текущим правилам и данным драйверов.
+pub async fn rebuild_range(
+    date_from: &str,
+    date_to: &str,
+) -> Result<StorageAllocationRebuildResponse> {
+    let mut cache = BasisCache::load().await?;
+    let mut response = StorageAllocationRebuildResponse::default();
+    for code in ALLOCATED_TURNOVER_CODES {
+        let sources = p910::list_with_filters(
+            Some(date_from.to_string()),
+            Some(date_to.to_string()),
+            None,
+            None,
+            Some(code.to_string()),
+            None,
+            None,
+            false,
+            None,
+            None,
+        )
+        .await?;
+        for source in sources.iter().filter(|s| is_allocated(s)) {
+            let finance_row = p903::get_by_id(&source.registrator_ref).await?;
+            let (rows, unallocated) =
+                allocate_row(&mut cache, source, finance_row.as_ref()).await?;
+            repository::replace_for_source(&source.id, rows).await?;
+            response.source_rows += 1;
+            if unallocated {
+                response.unallocated_rows += 1;
+            }
+        }
+    }
+    Ok(response)
+}
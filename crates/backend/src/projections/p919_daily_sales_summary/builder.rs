@@ -0,0 +1,157 @@
+//! Вклад документа продажи в дневную сводку.
+//!
+//! Суммы берутся из строк p904, которые проведение документа уже собрало, —
+//! поэтому сводка совпадает с p904 по выручке, комиссии и итогу. Штуки и признак
+//! заказа — из самого документа (в p904 количества нет).
+
+use chrono::Utc;
+use contracts::domain::a012_wb_sales::aggregate::WbSales;
+use contracts::domain::a014_ozon_transactions::aggregate::OzonTransactions;
+
+use super::contributions::Model as Contribution;
+use crate::projections::p904_sales_data::repository::Model as P904Model;
+
+pub const REG_A012: &str = "a012_wb_sales";
+pub const REG_A014: &str = "a014_ozon_transactions";
+
+/// Реквизиты документа, которых нет в строках p904.
+struct DocumentFacts<'a> {
+    registrator_ref: &'a str,
+    registrator_type: &'static str,
+    marketplace: &'static str,
+    organization_ref: &'a str,
+    orders_count: i64,
+    units: f64,
+}
+
+/// Без строк p904 документ в сводку не попадает.
+fn from_sales_data(facts: DocumentFacts<'_>, entries: &[P904Model]) -> Option<Contribution> {
+    let first = entries.first()?;
+    let date = first.date.get(..10).unwrap_or(&first.date).to_string();
+    Some(Contribution {
+        registrator_ref: facts.registrator_ref.to_string(),
+        registrator_type: facts.registrator_type.to_string(),
+        date,
+        marketplace: facts.marketplace.to_string(),
+        organization_ref: facts.organization_ref.to_string(),
+        orders_count: facts.orders_count,
+        units: facts.units,
+        gross: entries.iter().map(|e| e.customer_in + e.customer_out).sum(),
+        commission: entries.iter().map(|e| e.commission_out).sum(),
+        net: entries.iter().map(|e| e.total).sum(),
+        created_at: Utc::now().to_rfc3339(),
+    })
+}
+
+/// a012: один документ — одна продажа или один возврат.
+pub fn from_wb_sales(
+    document: &WbSales,
+    registrator_ref: &str,
+    entries: &[P904Model],
+) -> Option<Contribution> {
+    let is_return = document.is_customer_return;
+    let qty = document.line.qty.abs();
+    from_sales_data(
+        DocumentFacts {
+            registrator_ref,
+            registrator_type: REG_A012,
+            marketplace: "WB",
+            organization_ref: &document.header.organization_id,
+            orders_count: if is_return { 0 } else { 1 },
+            units: if is_return { -qty } else { qty },
+        },
+        entries,
+    )
+}
+
+/// a014: заказом считается транзакция `orders`, штуки — по товарам транзакции;
+/// у прочих типов (сервисы, компенсации) штук и заказов нет, только суммы.
+pub fn from_ozon_transactions(
+    document: &OzonTransactions,
+    registrator_ref: &str,
+    entries: &[P904Model],
+) -> Option<Contribution> {
+    let items = document.items.len() as f64;
+    let (orders_count, units) = match document.header.transaction_type.as_str() {
+        "orders" => (1, items),
+        "returns" => (0, -items),
+        _ => (0, 0.0),
+    };
+    from_sales_data(
+        DocumentFacts {
+            registrator_ref,
+            registrator_type: REG_A014,
+            marketplace: "OZON",
+            organization_ref: &document.header.organization_id,
+            orders_count,
+            units,
+        },
+        entries,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(date: &str, customer_in: f64, customer_out: f64, commission: f64) -> P904Model {
+        P904Model {
+            id: String::new(),
+            registrator_ref: "doc".to_string(),
+            registrator_type: "OZON_Transactions".to_string(),
+            date: date.to_string(),
+            connection_mp_ref: String::new(),
+            nomenclature_ref: String::new(),
+            marketplace_product_ref: String::new(),
+            customer_in,
+            customer_out,
+            coinvest_in: 0.0,
+            commission_out: commission,
+            acquiring_out: 0.0,
+            penalty_out: 0.0,
+            logistics_out: 0.0,
+            seller_out: 0.0,
+            price_full: 0.0,
+            price_list: 0.0,
+            price_return: 0.0,
+            commission_percent: 0.0,
+            coinvest_persent: 0.0,
+            total: customer_in + customer_out + commission,
+            cost: None,
+            document_no: String::new(),
+            article: String::new(),
+            posted_at: String::new(),
+        }
+    }
+
+    fn facts(orders_count: i64, units: f64) -> DocumentFacts<'static> {
+        DocumentFacts {
+            registrator_ref: "doc",
+            registrator_type: REG_A014,
+            marketplace: "OZON",
+            organization_ref: "org",
+            orders_count,
+            units,
+        }
+    }
+
+    #[test]
+    fn sums_lines_and_truncates_datetime_to_day() {
+        let entries = [
+            entry("2026-03-05 14:20:00", 600.0, 0.0, -60.0),
+            entry("2026-03-05 14:20:00", 400.0, 0.0, -40.0),
+        ];
+        let contribution = from_sales_data(facts(1, 2.0), &entries).unwrap();
+        assert_eq!(contribution.date, "2026-03-05");
+        assert_eq!(contribution.gross, 1000.0);
+        assert_eq!(contribution.commission, -100.0);
+        assert_eq!(contribution.net, 900.0);
+        assert_eq!(contribution.orders_count, 1);
+        assert_eq!(contribution.units, 2.0);
+    }
+
+    #[test]
+    fn document_without_lines_has_no_contribution() {
+        assert!(from_sales_data(facts(1, 1.0), &[]).is_none());
+    }
+}
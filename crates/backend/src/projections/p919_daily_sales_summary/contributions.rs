@@ -0,0 +1,66 @@
+//! Вклад документа в дневную сводку `p919_daily_sales_summary_src`.
+//!
+//! Одна строка на проведённый документ: по ней при перепроведении и отмене
+//! проведения из итога дня вычитается ровно то, что документ туда добавил.
+
+use anyhow::Result;
+use sea_orm::entity::prelude::*;
+use sea_orm::{ConnectionTrait, EntityTrait, Set};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "p919_daily_sales_summary_src")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub registrator_ref: String,
+    pub registrator_type: String,
+    pub date: String,
+    pub marketplace: String,
+    pub organization_ref: String,
+    pub orders_count: i64,
+    pub units: f64,
+    pub gross: f64,
+    pub commission: f64,
+    pub net: f64,
+    pub created_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+pub async fn get_with_conn<C: ConnectionTrait>(
+    db: &C,
+    registrator_ref: &str,
+) -> Result<Option<Model>> {
+    Ok(Entity::find_by_id(registrator_ref.to_string())
+        .one(db)
+        .await?)
+}
+
+pub async fn delete_with_conn<C: ConnectionTrait>(db: &C, registrator_ref: &str) -> Result<u64> {
+    let result = Entity::delete_by_id(registrator_ref.to_string())
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// Вставка вклада; вызывающий перед этим удаляет прежний вклад документа.
+pub async fn insert_with_conn<C: ConnectionTrait>(db: &C, entry: &Model) -> Result<()> {
+    ActiveModel {
+        registrator_ref: Set(entry.registrator_ref.clone()),
+        registrator_type: Set(entry.registrator_type.clone()),
+        date: Set(entry.date.clone()),
+        marketplace: Set(entry.marketplace.clone()),
+        organization_ref: Set(entry.organization_ref.clone()),
+        orders_count: Set(entry.orders_count),
+        units: Set(entry.units),
+        gross: Set(entry.gross),
+        commission: Set(entry.commission),
+        net: Set(entry.net),
+        created_at: Set(entry.created_at.clone()),
+    }
+    .insert(db)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,4 @@
+pub mod builder;
+pub mod contributions;
+pub mod repository;
+pub mod service;
@@ -0,0 +1,194 @@
+//! Репозиторий итогов `p919_daily_sales_summary`: день × маркетплейс × организация.
+
+use anyhow::Result;
+use chrono::Utc;
+use contracts::projections::p919_daily_sales_summary::dto::DailySalesSummaryDto;
+use sea_orm::entity::prelude::*;
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QueryOrder,
+    Statement,
+};
+
+use super::builder::{REG_A012, REG_A014};
+use super::contributions::Model as Contribution;
+use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "p919_daily_sales_summary")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub date: String,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub marketplace: String,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub organization_ref: String,
+    pub documents_count: i64,
+    pub orders_count: i64,
+    pub units: f64,
+    pub gross: f64,
+    pub commission: f64,
+    pub net: f64,
+    pub updated_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl From<Model> for DailySalesSummaryDto {
+    fn from(model: Model) -> Self {
+        Self {
+            date: model.date,
+            marketplace: model.marketplace,
+            organization_ref: model.organization_ref,
+            orders_count: model.orders_count,
+            units: model.units,
+            gross: model.gross,
+            commission: model.commission,
+            net: model.net,
+        }
+    }
+}
+
+/// Добавляет вклад документа в итог дня (`sign` = 1) или вычитает его (`sign` = -1).
+/// Строка, из которой ушёл последний документ, удаляется.
+pub async fn apply_contribution_with_conn<C: ConnectionTrait>(
+    db: &C,
+    contribution: &Contribution,
+    sign: i64,
+) -> Result<()> {
+    let k = sign as f64;
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO p919_daily_sales_summary \
+         (date, marketplace, organization_ref, documents_count, orders_count, units, gross, commission, net, updated_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+         ON CONFLICT(date, marketplace, organization_ref) DO UPDATE SET \
+         documents_count = documents_count + excluded.documents_count, \
+         orders_count = orders_count + excluded.orders_count, \
+         units = units + excluded.units, \
+         gross = gross + excluded.gross, \
+         commission = commission + excluded.commission, \
+         net = net + excluded.net, \
+         updated_at = excluded.updated_at",
+        vec![
+            contribution.date.clone().into(),
+            contribution.marketplace.clone().into(),
+            contribution.organization_ref.clone().into(),
+            sign.into(),
+            (sign * contribution.orders_count).into(),
+            (k * contribution.units).into(),
+            (k * contribution.gross).into(),
+            (k * contribution.commission).into(),
+            (k * contribution.net).into(),
+            Utc::now().to_rfc3339().into(),
+        ],
+    ))
+    .await?;
+    if sign < 0 {
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM p919_daily_sales_summary \
+             WHERE date = ? AND marketplace = ? AND organization_ref = ? AND documents_count <= 0",
+            vec![
+                contribution.date.clone().into(),
+                contribution.marketplace.clone().into(),
+                contribution.organization_ref.clone().into(),
+            ],
+        ))
+        .await?;
+    }
+    Ok(())
+}
+
+pub async fn list(
+    date_from: &str,
+    date_to: &str,
+    marketplace: Option<&str>,
+    organization_ref: Option<&str>,
+) -> Result<Vec<Model>> {
+    let mut condition = Condition::all()
+        .add(Column::Date.gte(date_from))
+        .add(Column::Date.lte(date_to));
+    if let Some(value) = marketplace.filter(|v| !v.is_empty()) {
+        condition = condition.add(Column::Marketplace.eq(value));
+    }
+    if let Some(value) = organization_ref.filter(|v| !v.is_empty()) {
+        condition = condition.add(Column::OrganizationRef.eq(value));
+    }
+    Ok(Entity::find()
+        .filter(condition)
+        .order_by_asc(Column::Date)
+        .order_by_asc(Column::Marketplace)
+        .all(get_connection())
+        .await?)
+}
+
+/// Пересобирает вклады и итоги с нуля по проведённым документам и строкам p904
+/// (включая архив). Возвращает (документов, строк итога).
+pub async fn rebuild_with_conn<C: ConnectionTrait>(db: &C) -> Result<(u64, u64)> {
+    let p904 = projection_archive::source("p904_sales_data", None);
+    let a012 = projection_archive::source("a012_wb_sales", None);
+    let now = Utc::now().to_rfc3339();
+    db.execute_unprepared("DELETE FROM p919_daily_sales_summary")
+        .await?;
+    db.execute_unprepared("DELETE FROM p919_daily_sales_summary_src")
+        .await?;
+
+    let wb_sql = format!(
+        "INSERT INTO p919_daily_sales_summary_src \
+         (registrator_ref, registrator_type, date, marketplace, organization_ref, orders_count, units, gross, commission, net, created_at) \
+         SELECT s.registrator_ref, ?, substr(MIN(s.date), 1, 10), 'WB', COALESCE(d.organization_id, ''), \
+                CASE WHEN d.is_customer_return THEN 0 ELSE 1 END, \
+                CASE WHEN d.is_customer_return THEN -ABS(COALESCE(d.qty, 0)) ELSE ABS(COALESCE(d.qty, 0)) END, \
+                SUM(s.customer_in + s.customer_out), SUM(s.commission_out), SUM(s.total), ? \
+         FROM {p904} s \
+         JOIN {a012} d ON d.id = s.registrator_ref \
+         WHERE s.registrator_type = 'WB_Sales' AND d.is_posted = 1 AND d.is_deleted = 0 \
+         GROUP BY s.registrator_ref"
+    );
+    let ozon_sql = format!(
+        "INSERT INTO p919_daily_sales_summary_src \
+         (registrator_ref, registrator_type, date, marketplace, organization_ref, orders_count, units, gross, commission, net, created_at) \
+         SELECT s.registrator_ref, ?, substr(MIN(s.date), 1, 10), 'OZON', \
+                COALESCE(json_extract(d.header_json, '$.organization_id'), ''), \
+                CASE json_extract(d.header_json, '$.transaction_type') WHEN 'orders' THEN 1 ELSE 0 END, \
+                CASE json_extract(d.header_json, '$.transaction_type') \
+                    WHEN 'orders' THEN json_array_length(d.items_json) \
+                    WHEN 'returns' THEN -json_array_length(d.items_json) \
+                    ELSE 0 END, \
+                SUM(s.customer_in + s.customer_out), SUM(s.commission_out), SUM(s.total), ? \
+         FROM {p904} s \
+         JOIN a014_ozon_transactions d ON d.id = s.registrator_ref \
+         WHERE s.registrator_type = 'OZON_Transactions' AND d.is_posted = 1 AND d.is_deleted = 0 \
+         GROUP BY s.registrator_ref"
+    );
+    let mut documents = 0;
+    for (sql, registrator_type) in [(wb_sql, REG_A012), (ozon_sql, REG_A014)] {
+        let result = db
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                &sql,
+                vec![registrator_type.into(), now.clone().into()],
+            ))
+            .await?;
+        documents += result.rows_affected();
+    }
+
+    let rows = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO p919_daily_sales_summary \
+             (date, marketplace, organization_ref, documents_count, orders_count, units, gross, commission, net, updated_at) \
+             SELECT date, marketplace, organization_ref, COUNT(*), SUM(orders_count), SUM(units), \
+                    SUM(gross), SUM(commission), SUM(net), ? \
+             FROM p919_daily_sales_summary_src \
+             GROUP BY date, marketplace, organization_ref",
+            vec![now.into()],
+        ))
+        .await?
+        .rows_affected();
+    Ok((documents, rows))
+}
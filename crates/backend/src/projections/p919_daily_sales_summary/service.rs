@@ -0,0 +1,51 @@
+//! Инкрементальное ведение дневной сводки продаж.
+//!
+//! Проведение документа заменяет его вклад: прежний вычитается из итога дня,
+//! новый добавляется — в той же транзакции, что и строки p904. Отмена
+//! проведения только вычитает. Полная пересборка нужна после миграции и для
+//! сверки, если итоги разошлись с p904.
+
+use anyhow::Result;
+use contracts::projections::p919_daily_sales_summary::dto::DailySalesSummaryRebuildResponse;
+use sea_orm::{ConnectionTrait, TransactionTrait};
+
+use super::contributions::{self, Model as Contribution};
+use super::repository;
+use crate::shared::data::db::get_connection;
+
+/// Заменяет вклад документа; `None` — документ из сводки убирается.
+pub async fn replace_for_registrator_with_conn<C: ConnectionTrait>(
+    db: &C,
+    registrator_ref: &str,
+    contribution: Option<&Contribution>,
+) -> Result<()> {
+    if let Some(previous) = contributions::get_with_conn(db, registrator_ref).await? {
+        repository::apply_contribution_with_conn(db, &previous, -1).await?;
+        contributions::delete_with_conn(db, registrator_ref).await?;
+    }
+    if let Some(contribution) = contribution {
+        contributions::insert_with_conn(db, contribution).await?;
+        repository::apply_contribution_with_conn(db, contribution, 1).await?;
+    }
+    Ok(())
+}
+
+/// Убирает документ из сводки вне транзакции проведения.
+pub async fn remove_for_registrator(registrator_ref: &str) -> Result<()> {
+    let txn = get_connection().begin().await?;
+    replace_for_registrator_with_conn(&txn, registrator_ref, None).await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+pub async fn rebuild() -> Result<DailySalesSummaryRebuildResponse> {
+    let txn = get_connection().begin().await?;
+    let (documents, rows) = repository::rebuild_with_conn(&txn).await?;
+    txn.commit().await?;
+    tracing::info!(
+        "P919 rebuilt: {} documents, {} summary rows",
+        documents,
+        rows
+    );
+    Ok(DailySalesSummaryRebuildResponse { documents, rows })
+}
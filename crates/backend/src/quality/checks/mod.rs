@@ -3,5 +3,7 @@ pub mod marketplace_product_ref_required;
 pub mod nomenclature_in_projections;
 pub mod p903_gl_integrity;
 pub mod p907_gl_coverage;
+pub mod posting_consistency;
 pub mod projection_orphan_registrators;
 pub mod registrator_registry;
+pub mod schema_drift;
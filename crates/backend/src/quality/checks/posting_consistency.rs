@@ -0,0 +1,267 @@
+//! ## Проверка: флаг `is_posted` ↔ строки проекций
+//!
+//! Проведённый документ обязан иметь строки в P900, непроведённый — не иметь ни
+//! одной. После аварийного завершения процесса флаг и проекции расходятся.
+//!
+//! - `posted_without_projection` — флаг стоит, строк нет. Тривиальный случай:
+//!   перепроведение восстанавливает строки, его выполняет `task035_posting_consistency`.
+//! - `projection_without_posting` — строки есть, флаг снят. Какая сторона верна,
+//!   неизвестно, поэтому расхождение только попадает в отчёт проверки.
+//!
+//! Правило применяется к документам, проведение которых всегда пишет строку P900
+//! (для FBS — только доставленные отправления). Документы и строки P900 читаются
+//! вместе с архивом: у архивированного документа строки тоже в архиве.
+
+use contracts::quality::{CheckMetric, CheckResult, QualityCheckInfo, ViolationItem};
+
+use crate::shared::data::projection_archive;
+
+pub const CHECK_ID: &str = "posting_consistency";
+
+const PROJECTION_TABLE: &str = "p900_sales_register";
+
+/// Сколько примеров нарушений отдаётся в UI.
+const SAMPLE_LIMIT: usize = 20;
+
+/// Тип документа, таблица агрегата и SQL-условие «проведение пишет строки».
+const RULES: [(&str, &str, &str); 3] = [
+    ("a009_ozon_returns", "a009_ozon_returns", "1 = 1"),
+    (
+        "a010_ozon_fbs_posting",
+        "a010_ozon_fbs_posting",
+        "d.status_norm = 'DELIVERED'",
+    ),
+    ("a012_wb_sales", "a012_wb_sales", "1 = 1"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InconsistencyKind {
+    PostedWithoutProjection,
+    ProjectionWithoutPosting,
+}
+
+impl InconsistencyKind {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::PostedWithoutProjection => "posted_without_projection",
+            Self::ProjectionWithoutPosting => "projection_without_posting",
+        }
+    }
+
+    /// Исправляется перепроведением без выбора стороны.
+    pub fn is_trivial(self) -> bool {
+        matches!(self, Self::PostedWithoutProjection)
+    }
+}
+
+/// Документ, у которого флаг проведения не совпадает с проекцией.
+#[derive(Debug, Clone)]
+pub struct Inconsistency {
+    pub registrator_type: &'static str,
+    pub document_id: String,
+    pub kind: InconsistencyKind,
+}
+
+/// Сверяет флаг с наличием строк; `None` — документ согласован.
+pub fn classify(is_posted: bool, expects_rows: bool, has_rows: bool) -> Option<InconsistencyKind> {
+    match (is_posted, has_rows) {
+        (true, false) if expects_rows => Some(InconsistencyKind::PostedWithoutProjection),
+        (false, true) => Some(InconsistencyKind::ProjectionWithoutPosting),
+        _ => None,
+    }
+}
+
+pub fn info() -> QualityCheckInfo {
+    QualityCheckInfo {
+        code: String::new(),
+        id: CHECK_ID.to_string(),
+        name: "Флаг проведения ↔ строки проекций".to_string(),
+        description:
+            "Находит документы a009/a010/a012, отмеченные проведёнными, но без строк P900, \
+             и непроведённые документы, строки которых остались в P900. Первые ежедневно \
+             перепроводит задача task035, вторые требуют решения пользователя."
+                .to_string(),
+        category: "Целостность проекций".to_string(),
+    }
+}
+
+async fn count_documents(table: &str) -> anyhow::Result<i64> {
+    use sea_orm::{ConnectionTrait, Statement};
+
+    let conn = crate::shared::data::db::get_connection();
+    let rows = conn
+        .query_all(Statement::from_string(
+            sea_orm::DatabaseBackend::Sqlite,
+            format!(
+                "SELECT CAST(COUNT(*) AS INTEGER) AS cnt FROM {} WHERE is_deleted = 0",
+                projection_archive::source(table, None)
+            ),
+        ))
+        .await?;
+    Ok(rows
+        .first()
+        .and_then(|r| r.try_get::<i64>("", "cnt").ok())
+        .unwrap_or(0))
+}
+
+async fn load_rule(
+    registrator_type: &'static str,
+    table: &str,
+    expects_rows: &str,
+) -> anyhow::Result<Vec<Inconsistency>> {
+    load_rule_with_conn(
+        crate::shared::data::db::get_connection(),
+        registrator_type,
+        table,
+        expects_rows,
+    )
+    .await
+}
+
+async fn load_rule_with_conn<C: sea_orm::ConnectionTrait>(
+    conn: &C,
+    registrator_type: &'static str,
+    table: &str,
+    expects_rows: &str,
+) -> anyhow::Result<Vec<Inconsistency>> {
+    use sea_orm::Statement;
+
+    let documents = projection_archive::source(table, None);
+    let projection = projection_archive::source(PROJECTION_TABLE, None);
+    let sql = format!(
+        r#"SELECT d.id AS document_id,
+                  d.is_posted AS is_posted,
+                  CASE WHEN {expects_rows} THEN 1 ELSE 0 END AS expects_rows,
+                  EXISTS (SELECT 1 FROM {projection} p WHERE p.registrator_ref = d.id) AS has_rows
+           FROM {documents} d
+           WHERE d.is_deleted = 0
+             AND (d.is_posted = 1) <> EXISTS (
+                 SELECT 1 FROM {projection} p WHERE p.registrator_ref = d.id
+             )"#
+    );
+    let rows = conn
+        .query_all(Statement::from_string(
+            sea_orm::DatabaseBackend::Sqlite,
+            sql,
+        ))
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let kind = classify(
+                row.try_get::<bool>("", "is_posted").unwrap_or(false),
+                row.try_get::<i32>("", "expects_rows").unwrap_or(0) != 0,
+                row.try_get::<i32>("", "has_rows").unwrap_or(0) != 0,
+            )?;
+            Some(Inconsistency {
+                registrator_type,
+                document_id: row.try_get("", "document_id").ok()?,
+                kind,
+            })
+        })
+        .collect())
+}
+
+/// Все расхождения по всем типам документов правила.
+pub async fn find_inconsistencies() -> anyhow::Result<Vec<Inconsistency>> {
+    let mut items = Vec::new();
+    for (registrator_type, table, expects_rows) in RULES {
+        items.extend(load_rule(registrator_type, table, expects_rows).await?);
+    }
+    Ok(items)
+}
+
+pub async fn run() -> anyhow::Result<CheckResult> {
+    let mut metrics = Vec::new();
+    let mut violations = Vec::new();
+    for (registrator_type, table, expects_rows) in RULES {
+        let items = load_rule(registrator_type, table, expects_rows).await?;
+        metrics.push(CheckMetric {
+            label: super::registrator_registry::get_meta(registrator_type)
+                .type_label
+                .to_string(),
+            population: count_documents(table).await?,
+            violations: items.len() as i64,
+            unit: "документов".to_string(),
+        });
+        for item in items {
+            if violations.len() >= SAMPLE_LIMIT {
+                break;
+            }
+            violations.push(ViolationItem {
+                violation_type: item.kind.code().to_string(),
+                gl_id: None,
+                projection_id: None,
+                projection_table: Some(PROJECTION_TABLE.to_string()),
+                detail: Some(format!("{} {}", item.registrator_type, item.document_id)),
+            });
+        }
+    }
+
+    let population_total = metrics.iter().map(|m| m.population).sum();
+    let violations_total = metrics.iter().map(|m| m.violations).sum();
+
+    Ok(CheckResult {
+        check_id: CHECK_ID.to_string(),
+        run_at: chrono::Utc::now(),
+        population_total,
+        violations_total,
+        metrics,
+        violations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flag_and_rows_must_agree() {
+        assert_eq!(classify(true, true, true), None);
+        assert_eq!(classify(false, true, false), None);
+        assert_eq!(
+            classify(true, true, false),
+            Some(InconsistencyKind::PostedWithoutProjection)
+        );
+        assert_eq!(
+            classify(false, false, true),
+            Some(InconsistencyKind::ProjectionWithoutPosting)
+        );
+    }
+
+    #[tokio::test]
+    async fn archived_documents_and_rows_are_not_reported() {
+        use sea_orm::{ConnectOptions, ConnectionTrait, Database};
+
+        projection_archive::set_cached_boundary(Some("2025-01".to_string()));
+        let mut options = ConnectOptions::new("sqlite::memory:");
+        options.max_connections(1);
+        let db = Database::connect(options).await.unwrap();
+        db.execute_unprepared(
+            "CREATE TABLE a012_wb_sales (id TEXT PRIMARY KEY, is_posted INTEGER, is_deleted INTEGER);
+             CREATE TABLE a012_wb_sales_archive (id TEXT PRIMARY KEY, is_posted INTEGER, is_deleted INTEGER);
+             CREATE TABLE p900_sales_register (registrator_ref TEXT);
+             CREATE TABLE p900_sales_register_archive (registrator_ref TEXT);
+             INSERT INTO a012_wb_sales_archive VALUES ('archived', 1, 0);
+             INSERT INTO p900_sales_register_archive VALUES ('archived');
+             INSERT INTO a012_wb_sales VALUES ('hot', 1, 0), ('lost', 1, 0);
+             INSERT INTO p900_sales_register VALUES ('hot');",
+        )
+        .await
+        .unwrap();
+
+        let items = load_rule_with_conn(&db, "a012_wb_sales", "a012_wb_sales", "1 = 1")
+            .await
+            .unwrap();
+        let reported: Vec<&str> = items.iter().map(|i| i.document_id.as_str()).collect();
+        assert_eq!(reported, vec!["lost"]);
+        assert_eq!(items[0].kind, InconsistencyKind::PostedWithoutProjection);
+    }
+
+    #[test]
+    fn posted_document_without_expected_rows_is_consistent() {
+        // Недоставленное FBS-отправление проводится без движений.
+        assert_eq!(classify(true, false, false), None);
+    }
+}
@@ -3,6 +3,9 @@
 //! Находит строки проекций, где `registrator_ref` указывает на документ,
 //! которого уже нет в исходном агрегате. Такие строки нельзя исправить
 //! перепроведением, их нужно удалить из проекции.
+//!
+//! P900/P904 хранят тип регистратора в историческом виде (`WB_Sales`, `OZON_FBS` …);
+//! перед поиском документа он сопоставляется с кодом агрегата.
 
 use contracts::quality::{
     BreakdownRow, CheckBreakdown, CheckMetric, CheckResult, NipCleanupResult, NipGroupsResponse,
@@ -11,7 +14,9 @@ use contracts::quality::{
 
 pub const CHECK_ID: &str = "projection_orphan_registrators";
 
-const SOURCES: [(&str, &str); 3] = [
+const SOURCES: [(&str, &str); 5] = [
+    ("p900_sales_register", "p900 — Регистр продаж МП"),
+    ("p904_sales_data", "p904 — Данные продаж"),
     (
         "p909_mp_order_line_turnovers",
         "p909 — Обороты строк заказов МП",
@@ -32,7 +37,7 @@ pub fn info() -> QualityCheckInfo {
         id: CHECK_ID.to_string(),
         name: "Cтроки проекций без регистраторов".to_string(),
         description:
-            "Находит строки p909/p911/p913, где документ-регистратор уже отсутствует в исходном агрегате. \
+            "Находит строки p900/p904/p909/p911/p913, где документ-регистратор уже отсутствует в исходном агрегате. \
              Такие строки можно удалить из проекции."
                 .to_string(),
         category: "Целостность проекций".to_string(),
@@ -51,7 +56,9 @@ pub fn list_sources() -> Vec<QualityCheckSource> {
 
 fn allowed_table(table: &str) -> anyhow::Result<&str> {
     match table {
-        "p909_mp_order_line_turnovers"
+        "p900_sales_register"
+        | "p904_sales_data"
+        | "p909_mp_order_line_turnovers"
         | "p911_wb_advert_by_items"
         | "p913_wb_advert_order_attr" => Ok(table),
         other => Err(anyhow::anyhow!(
@@ -61,6 +68,23 @@ fn allowed_table(table: &str) -> anyhow::Result<&str> {
     }
 }
 
+/// Колонки типа регистратора и даты строки: в P900/P904 они названы иначе,
+/// чем в оборотных проекциях.
+fn registrator_columns(table: &str) -> (&'static str, &'static str) {
+    match table {
+        "p900_sales_register" => ("document_type", "sale_date"),
+        "p904_sales_data" => ("registrator_type", "date"),
+        _ => ("registrator_type", "entry_date"),
+    }
+}
+
+/// Код агрегата, в котором ищется документ: исторические типы P900/P904
+/// сопоставляются, коды агрегатов остаются как есть.
+fn source_type(registrator_type: &str) -> &str {
+    contracts::projections::source_document::source_aggregate(registrator_type)
+        .unwrap_or(registrator_type)
+}
+
 fn allowed_sort_field(field: &str) -> &str {
     match field {
         "missing_count" | "registrator_type" | "registrator_ref" | "min_entry_date"
@@ -70,19 +94,26 @@ fn allowed_sort_field(field: &str) -> &str {
 }
 
 async fn load_orphan_groups(table: &str) -> anyhow::Result<Vec<NipRegistratorGroup>> {
-    use sea_orm::{ConnectionTrait, Statement};
+    load_orphan_groups_with_conn(crate::shared::data::db::get_connection(), table).await
+}
+
+async fn load_orphan_groups_with_conn<C: sea_orm::ConnectionTrait>(
+    conn: &C,
+    table: &str,
+) -> anyhow::Result<Vec<NipRegistratorGroup>> {
+    use sea_orm::Statement;
 
     let table = allowed_table(table)?;
-    let conn = crate::shared::data::db::get_connection();
+    let (type_col, date_col) = registrator_columns(table);
     let sql = format!(
         r#"SELECT
-               registrator_type,
+               {type_col} AS registrator_type,
                registrator_ref,
                CAST(COUNT(*) AS INTEGER) AS missing_count,
-               MIN(entry_date) AS min_entry_date,
-               MAX(entry_date) AS max_entry_date
+               MIN({date_col}) AS min_entry_date,
+               MAX({date_col}) AS max_entry_date
            FROM {table}
-           GROUP BY registrator_type, registrator_ref"#
+           GROUP BY {type_col}, registrator_ref"#
     );
 
     let rows = conn
@@ -97,8 +128,9 @@ async fn load_orphan_groups(table: &str) -> anyhow::Result<Vec<NipRegistratorGro
         let registrator_type: String = row.try_get("", "registrator_type").unwrap_or_default();
         let registrator_ref: String = row.try_get("", "registrator_ref").unwrap_or_default();
 
-        let source_exists = super::registrator_registry::source_document_exists(
-            &registrator_type,
+        let source_exists = super::registrator_registry::source_document_exists_with_conn(
+            conn,
+            source_type(&registrator_type),
             &registrator_ref,
         )
         .await
@@ -110,7 +142,7 @@ async fn load_orphan_groups(table: &str) -> anyhow::Result<Vec<NipRegistratorGro
         let missing_count: i64 = row.try_get("", "missing_count").unwrap_or(0);
         let min_entry_date: Option<String> = row.try_get("", "min_entry_date").ok();
         let max_entry_date: Option<String> = row.try_get("", "max_entry_date").ok();
-        let meta = super::registrator_registry::get_meta(&registrator_type);
+        let meta = super::registrator_registry::get_meta(source_type(&registrator_type));
         let display_short = if registrator_ref.len() > 8 {
             format!("{}…", &registrator_ref[..8])
         } else {
@@ -190,7 +222,7 @@ pub async fn breakdowns() -> anyhow::Result<Vec<CheckBreakdown>> {
     let mut by_type: BTreeMap<String, i64> = BTreeMap::new();
     for (table, _) in SOURCES {
         for g in load_orphan_groups(table).await? {
-            let label = super::registrator_registry::get_meta(&g.registrator_type)
+            let label = super::registrator_registry::get_meta(source_type(&g.registrator_type))
                 .type_label
                 .to_string();
             *by_type.entry(label).or_insert(0) += g.missing_count;
@@ -272,6 +304,25 @@ pub async fn list_rows(
     let table = allowed_table(projection_table)?;
     let conn = crate::shared::data::db::get_connection();
     let sql = match table {
+        "p900_sales_register" => format!(
+            r#"SELECT (marketplace || ':' || document_no || ':' || line_id) AS id,
+                      sale_date AS entry_date, document_type AS turnover_code,
+                      COALESCE(amount_line, 0) AS amount, connection_mp_ref,
+                      'Артикул' AS context_label, seller_sku AS context_value
+               FROM p900_sales_register
+               WHERE registrator_ref = '{registrator_ref}'
+               ORDER BY sale_date, line_id
+               LIMIT 500"#
+        ),
+        "p904_sales_data" => format!(
+            r#"SELECT id, date AS entry_date, registrator_type AS turnover_code,
+                      total AS amount, connection_mp_ref,
+                      'Артикул' AS context_label, article AS context_value
+               FROM p904_sales_data
+               WHERE registrator_ref = '{registrator_ref}'
+               ORDER BY date, id
+               LIMIT 500"#
+        ),
         "p909_mp_order_line_turnovers" => format!(
             r#"SELECT id, entry_date, turnover_code, amount, connection_mp_ref,
                       'Заказ' AS context_label, order_key AS context_value
@@ -325,17 +376,30 @@ pub async fn cleanup(
     projection_table: &str,
     registrator_refs: &[String],
 ) -> anyhow::Result<NipCleanupResult> {
-    use sea_orm::{ConnectionTrait, Statement};
+    cleanup_with_conn(
+        crate::shared::data::db::get_connection(),
+        projection_table,
+        registrator_refs,
+    )
+    .await
+}
+
+async fn cleanup_with_conn<C: sea_orm::ConnectionTrait>(
+    conn: &C,
+    projection_table: &str,
+    registrator_refs: &[String],
+) -> anyhow::Result<NipCleanupResult> {
+    use sea_orm::Statement;
 
     let table = allowed_table(projection_table)?;
-    let conn = crate::shared::data::db::get_connection();
+    let (type_col, _) = registrator_columns(table);
     let requested = registrator_refs.len();
     let mut deleted_rows = 0usize;
     let mut errors = Vec::new();
 
     for registrator_ref in registrator_refs {
         let check_sql = format!(
-            r#"SELECT registrator_type
+            r#"SELECT {type_col} AS registrator_type
                FROM {table}
                WHERE registrator_ref = ?
                LIMIT 1"#
@@ -354,17 +418,20 @@ pub async fn cleanup(
             .try_get("", "registrator_type")
             .unwrap_or_default();
 
-        let source_exists =
-            super::registrator_registry::source_document_exists(&registrator_type, registrator_ref)
-                .await
-                .unwrap_or(true);
+        let source_exists = super::registrator_registry::source_document_exists_with_conn(
+            conn,
+            source_type(&registrator_type),
+            registrator_ref,
+        )
+        .await
+        .unwrap_or(true);
         if source_exists {
             errors.push(format!("{registrator_ref}: исходный документ существует"));
             continue;
         }
 
         let delete_sql =
-            format!("DELETE FROM {table} WHERE registrator_type = ? AND registrator_ref = ?");
+            format!("DELETE FROM {table} WHERE {type_col} = ? AND registrator_ref = ?");
         let result = conn
             .execute(Statement::from_sql_and_values(
                 sea_orm::DatabaseBackend::Sqlite,
@@ -381,3 +448,58 @@ pub async fn cleanup(
         errors,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection, Statement};
+
+    async fn p909_row_count(db: &DatabaseConnection) -> i64 {
+        db.query_one(Statement::from_string(
+            sea_orm::DatabaseBackend::Sqlite,
+            "SELECT COUNT(*) AS cnt FROM p909_mp_order_line_turnovers",
+        ))
+        .await
+        .unwrap()
+        .unwrap()
+        .try_get::<i64>("", "cnt")
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn archived_document_keeps_its_projection_rows() {
+        crate::shared::data::projection_archive::set_cached_boundary(Some("2025-01".to_string()));
+        let mut options = ConnectOptions::new("sqlite::memory:");
+        options.max_connections(1);
+        let db = Database::connect(options).await.unwrap();
+        db.execute_unprepared(
+            "CREATE TABLE a012_wb_sales (id TEXT PRIMARY KEY, sale_date TEXT);
+             CREATE TABLE a012_wb_sales_archive (id TEXT PRIMARY KEY, sale_date TEXT);
+             CREATE TABLE p909_mp_order_line_turnovers (
+                 id TEXT PRIMARY KEY, registrator_type TEXT, registrator_ref TEXT, entry_date TEXT);
+             INSERT INTO a012_wb_sales_archive VALUES ('d-archived', '2024-11-05');
+             INSERT INTO p909_mp_order_line_turnovers
+                 VALUES ('t1', 'a012_wb_sales', 'a012:d-archived', '2024-11-05'),
+                        ('t2', 'a012_wb_sales', 'a012:d-deleted', '2024-11-06');",
+        )
+        .await
+        .unwrap();
+
+        let groups = load_orphan_groups_with_conn(&db, "p909_mp_order_line_turnovers")
+            .await
+            .unwrap();
+        let refs: Vec<&str> = groups.iter().map(|g| g.registrator_ref.as_str()).collect();
+        assert_eq!(refs, vec!["a012:d-deleted"]);
+
+        let result = cleanup_with_conn(
+            &db,
+            "p909_mp_order_line_turnovers",
+            &["a012:d-archived".to_string(), "a012:d-deleted".to_string()],
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.deleted_rows, 1);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(p909_row_count(&db).await, 1);
+    }
+}
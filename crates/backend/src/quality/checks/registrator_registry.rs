@@ -31,6 +31,21 @@ fn document_id_from_registrator_ref(registrator_ref: &str) -> &str {
 /// Для неизвестных типов возвращает `can_post = false` и отсутствие tab-prefix.
 pub fn get_meta(registrator_type: &str) -> RegistratorMeta {
     match registrator_type {
+        "a009_ozon_returns" => RegistratorMeta {
+            type_label: "Возвраты Ozon",
+            can_post: true,
+            tab_key_prefix: Some("a009_ozon_returns_details"),
+        },
+        "a010_ozon_fbs_posting" => RegistratorMeta {
+            type_label: "Отправления Ozon FBS",
+            can_post: true,
+            tab_key_prefix: Some("a010_ozon_fbs_posting_details"),
+        },
+        "a011_ozon_fbo_posting" => RegistratorMeta {
+            type_label: "Отправления Ozon FBO",
+            can_post: true,
+            tab_key_prefix: Some("a011_ozon_fbo_posting_details"),
+        },
         "a012_wb_sales" => RegistratorMeta {
             type_label: "Продажи WB",
             can_post: true,
@@ -88,6 +103,15 @@ pub async fn repost_document(registrator_type: &str, registrator_ref: &str) -> a
         .map_err(|e| anyhow::anyhow!("Invalid registrator_ref '{}': {}", registrator_ref, e))?;
 
     match registrator_type {
+        "a009_ozon_returns" => {
+            crate::domain::a009_ozon_returns::posting::post_document(uuid).await?;
+        }
+        "a010_ozon_fbs_posting" => {
+            crate::domain::a010_ozon_fbs_posting::posting::post_document(uuid).await?;
+        }
+        "a011_ozon_fbo_posting" => {
+            crate::domain::a011_ozon_fbo_posting::posting::post_document(uuid).await?;
+        }
         "a012_wb_sales" => {
             crate::domain::a012_wb_sales::posting::post_document(uuid).await?;
         }
@@ -224,9 +248,28 @@ pub async fn source_document_exists(
     registrator_type: &str,
     registrator_ref: &str,
 ) -> anyhow::Result<bool> {
-    use sea_orm::{ConnectionTrait, Statement};
+    source_document_exists_with_conn(
+        crate::shared::data::db::get_connection(),
+        registrator_type,
+        registrator_ref,
+    )
+    .await
+}
+
+/// [`source_document_exists`] на переданном соединении. Документ ищется и в
+/// архиве (`projection_archive::source` без периода): строки проекций
+/// архивированного документа не сиротские.
+pub async fn source_document_exists_with_conn<C: sea_orm::ConnectionTrait>(
+    conn: &C,
+    registrator_type: &str,
+    registrator_ref: &str,
+) -> anyhow::Result<bool> {
+    use sea_orm::Statement;
 
     let table = match registrator_type {
+        "a009_ozon_returns" => "a009_ozon_returns",
+        "a010_ozon_fbs_posting" => "a010_ozon_fbs_posting",
+        "a011_ozon_fbo_posting" => "a011_ozon_fbo_posting",
         "a012_wb_sales" => "a012_wb_sales",
         "a013_ym_order" => "a013_ym_order",
         "a014_ozon_transactions" => "a014_ozon_transactions",
@@ -239,8 +282,10 @@ pub async fn source_document_exists(
     };
 
     let document_id = document_id_from_registrator_ref(registrator_ref);
-    let sql = format!("SELECT COUNT(*) AS cnt FROM {table} WHERE id = ?");
-    let conn = crate::shared::data::db::get_connection();
+    let sql = format!(
+        "SELECT COUNT(*) AS cnt FROM {} WHERE id = ?",
+        crate::shared::data::projection_archive::source(table, None)
+    );
     let rows = conn
         .query_all(Statement::from_sql_and_values(
             sea_orm::DatabaseBackend::Sqlite,
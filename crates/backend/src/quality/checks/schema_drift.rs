@@ -0,0 +1,98 @@
+//! ## Проверка: расхождения схемы ответов маркетплейсов
+//!
+//! Импорт сверяет страницы ответов WB/Ozon с таблицами `field_mapping` и пишет
+//! новые/пропавшие поля и неразобранные строки в `sys_schema_drift`
+//! (см. `shared::marketplaces::schema_guard`). Каждое открытое расхождение —
+//! нарушение: поле нужно добавить в таблицу сопоставления (или в `unmapped`)
+//! либо принять расхождение кнопкой в детализации проверки.
+
+use contracts::quality::{
+    CheckMetric, CheckResult, QualityCheckInfo, ViolationAcknowledgeResult, ViolationItem,
+};
+
+use crate::system::schema_drift::repository::{self, STATUS_OPEN};
+
+pub const CHECK_ID: &str = "schema_drift";
+
+/// Сколько открытых расхождений отдаётся в UI (все — действия «Принять»).
+const VIOLATION_LIMIT: usize = 200;
+
+pub fn info() -> QualityCheckInfo {
+    QualityCheckInfo {
+        code: String::new(),
+        id: CHECK_ID.to_string(),
+        name: "Схема ответов API маркетплейсов".to_string(),
+        description: "Новые поля, которые импорт не переносит, пропавшие поля и строки, \
+             не разобранные клиентом API. Записываются при каждом импорте WB продаж, \
+             WB заказов и транзакций Ozon; принятые расхождения не считаются нарушением."
+            .to_string(),
+        category: "Импорт".to_string(),
+    }
+}
+
+pub async fn run() -> anyhow::Result<CheckResult> {
+    let rows = repository::list().await?;
+
+    let mut metrics: Vec<CheckMetric> = Vec::new();
+    for row in &rows {
+        let is_open = row.status == STATUS_OPEN;
+        match metrics.iter_mut().find(|m| m.label == row.endpoint) {
+            Some(metric) => {
+                metric.population += 1;
+                metric.violations += i64::from(is_open);
+            }
+            None => metrics.push(CheckMetric {
+                label: row.endpoint.clone(),
+                population: 1,
+                violations: i64::from(is_open),
+                unit: "полей".to_string(),
+            }),
+        }
+    }
+
+    let violations = rows
+        .into_iter()
+        .filter(|row| row.status == STATUS_OPEN)
+        .take(VIOLATION_LIMIT)
+        .map(|row| ViolationItem {
+            violation_type: row.kind,
+            gl_id: None,
+            projection_id: Some(row.id),
+            projection_table: Some(row.endpoint),
+            detail: Some(match row.example_value {
+                Some(example) => format!(
+                    "{} = {} · {} раз, последний {}",
+                    row.field, example, row.occurrences, row.last_seen_at
+                ),
+                None => format!(
+                    "{} · {} раз, последний {}",
+                    row.field, row.occurrences, row.last_seen_at
+                ),
+            }),
+        })
+        .collect();
+
+    let population_total = metrics.iter().map(|m| m.population).sum();
+    let violations_total = metrics.iter().map(|m| m.violations).sum();
+
+    Ok(CheckResult {
+        check_id: CHECK_ID.to_string(),
+        run_at: chrono::Utc::now(),
+        population_total,
+        violations_total,
+        metrics,
+        violations,
+    })
+}
+
+/// Принимает расхождения по id записей `sys_schema_drift`.
+pub async fn acknowledge(
+    ids: &[String],
+    username: &str,
+) -> anyhow::Result<ViolationAcknowledgeResult> {
+    let acknowledged = repository::acknowledge(ids, username).await?;
+    Ok(ViolationAcknowledgeResult {
+        requested: ids.len(),
+        acknowledged: acknowledged as usize,
+    })
+}
@@ -30,6 +30,8 @@
 //! | `nomenclature_in_projections` | Заполненность номенклатуры в проекциях | Строки p909/p911/p913, где `nomenclature_ref IS NULL` |
 //! | `gl_projection_integrity` | Целостность GL ↔ ProjectionLinked-проекции | orphan_gl / orphan_projection / amount_mismatch для p909/p910/p911/p913 |
 //! | `p903_gl_integrity` | Целостность GL ↔ p903 (ExternalLinked) | orphan_gl / amount_mismatch для p903_wb_finance_report |
+//! | `posting_consistency` | Флаг проведения ↔ строки проекций | a009/a010/a012 с `is_posted`, не совпадающим с наличием строк P900 |
+//! | `schema_drift` | Схема ответов API маркетплейсов | Открытые записи `sys_schema_drift`: новые/пропавшие поля, неразобранные строки |
 //!
 //! ## Добавление новой проверки
 //!
@@ -43,6 +45,7 @@ pub mod checks;
 use contracts::quality::{
     CheckDetails, CheckResult, NipCleanupRequest, NipCleanupResult, NipGroupsResponse,
     NipProjectionRow, NipRepostRequest, NipRepostResult, QualityCheckInfo, QualityCheckSource,
+    ViolationAcknowledgeRequest, ViolationAcknowledgeResult,
 };
 
 /// Возвращает список всех зарегистрированных проверок.
@@ -54,6 +57,8 @@ pub fn list_checks() -> Vec<QualityCheckInfo> {
         checks::gl_projection_integrity::info(),
         checks::p903_gl_integrity::info(),
         checks::p907_gl_coverage::info(),
+        checks::posting_consistency::info(),
+        checks::schema_drift::info(),
     ];
 
     for (idx, check) in checks.iter_mut().enumerate() {
@@ -80,6 +85,8 @@ pub async fn run_check(id: &str) -> anyhow::Result<CheckResult> {
         checks::gl_projection_integrity::CHECK_ID => checks::gl_projection_integrity::run().await,
         checks::p903_gl_integrity::CHECK_ID => checks::p903_gl_integrity::run().await,
         checks::p907_gl_coverage::CHECK_ID => checks::p907_gl_coverage::run().await,
+        checks::posting_consistency::CHECK_ID => checks::posting_consistency::run().await,
+        checks::schema_drift::CHECK_ID => checks::schema_drift::run().await,
         other => Err(anyhow::anyhow!("NOT_FOUND: Unknown check id: {}", other)),
     }
 }
@@ -212,3 +219,17 @@ pub async fn bulk_repost(
         other => Err(anyhow::anyhow!("NOT_FOUND: Unknown check id: {}", other)),
     }
 }
+
+/// Принимает нарушения проверки: они остаются в журнале, но не считаются проблемой.
+pub async fn acknowledge_violations(
+    check_id: &str,
+    request: &ViolationAcknowledgeRequest,
+    username: &str,
+) -> anyhow::Result<ViolationAcknowledgeResult> {
+    match check_id {
+        checks::schema_drift::CHECK_ID => {
+            checks::schema_drift::acknowledge(&request.ids, username).await
+        }
+        other => Err(anyhow::anyhow!("NOT_FOUND: Unknown check id: {}", other)),
+    }
+}
@@ -129,6 +129,13 @@ pub fn get_account(code: &str) -> Option<&'static AccountDef> {
     ACCOUNT_REGISTRY.iter().find(|a| a.code == code)
 }
 
+/// Счёт относится к отчёту о прибылях и убытках. Субсчёт, которого нет в
+/// плане счетов (`4404`, `9102`), определяется по двузначному счёту-группе.
+pub fn is_profit_loss_account(code: &str) -> bool {
+    let account = get_account(code).or_else(|| code.get(..2).and_then(get_account));
+    account.is_some_and(|a| a.section == StatementSection::ProfitLoss)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,4 +165,14 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn profit_loss_accounts_resolve_through_group() {
+        assert!(is_profit_loss_account("9001"));
+        assert!(is_profit_loss_account("4404"));
+        assert!(is_profit_loss_account("9102"));
+        assert!(!is_profit_loss_account("7609"));
+        assert!(!is_profit_loss_account("9601"));
+        assert!(!is_profit_loss_account(""));
+    }
 }
@@ -0,0 +1,119 @@
+//! Перевод вычисляемых колонок (`contracts::shared::calc_column`) в выражения SELECT.
+//!
+//! Список описывает SQL-выражение каждого поля (как для быстрого фильтра). Поля и
+//! операторы берутся только из разобранного дерева, константы — `f64`, поэтому в SQL
+//! не попадает ничего из исходной строки пользователя.
+
+use contracts::shared::calc_column::{parse_param, CalcColumn, CalcExpr, CalcField, CalcOp};
+use sea_orm::QueryResult;
+
+/// Колонки из параметра `calc` в виде, готовом для list-запроса.
+#[derive(Debug, Clone, Default)]
+pub struct CalcSelect {
+    pub names: Vec<String>,
+    /// Выражения по порядку колонок; в SELECT идут как `calc_0`, `calc_1`, ...
+    pub exprs: Vec<String>,
+}
+
+impl CalcSelect {
+    /// Фрагмент `, <expr> AS calc_0, ...` для добавления в конец списка SELECT.
+    pub fn select_sql(&self) -> String {
+        self.exprs
+            .iter()
+            .enumerate()
+            .map(|(i, expr)| format!(", CAST({} AS REAL) AS calc_{}", expr, i))
+            .collect()
+    }
+
+    /// Значения вычисляемых колонок строки (пусто — NULL).
+    pub fn read(&self, row: &QueryResult) -> Vec<Option<f64>> {
+        (0..self.exprs.len())
+            .map(|i| {
+                row.try_get::<Option<f64>>("", &format!("calc_{}", i))
+                    .ok()
+                    .flatten()
+            })
+            .collect()
+    }
+}
+
+fn expr_sql(expr: &CalcExpr, columns: &[(&str, &str)]) -> Result<String, String> {
+    Ok(match expr {
+        CalcExpr::Number(n) => format!("{:?}", n),
+        CalcExpr::Field(key) => columns
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, sql)| sql.to_string())
+            .ok_or_else(|| format!("Поле «{}» не поддерживается списком", key))?,
+        CalcExpr::Neg(inner) => format!("(-{})", expr_sql(inner, columns)?),
+        CalcExpr::Binary(left, CalcOp::Div, right) => format!(
+            "(CAST({} AS REAL) / NULLIF({}, 0))",
+            expr_sql(left, columns)?,
+            expr_sql(right, columns)?
+        ),
+        CalcExpr::Binary(left, op, right) => format!(
+            "({} {} {})",
+            expr_sql(left, columns)?,
+            op.symbol(),
+            expr_sql(right, columns)?
+        ),
+    })
+}
+
+/// SQL-выражения для проверенных колонок.
+pub fn build_select(
+    columns: &[CalcColumn],
+    sql_columns: &[(&str, &str)],
+) -> Result<CalcSelect, String> {
+    let mut select = CalcSelect::default();
+    for column in columns {
+        select.exprs.push(expr_sql(&column.expr, sql_columns)?);
+        select.names.push(column.def.name.clone());
+    }
+    Ok(select)
+}
+
+/// Разбор параметра `calc` списка и перевод в SQL.
+pub fn from_param(
+    raw: &str,
+    fields: &[CalcField],
+    sql_columns: &[(&str, &str)],
+) -> Result<CalcSelect, String> {
+    if raw.trim().is_empty() {
+        return Ok(CalcSelect::default());
+    }
+    build_select(&parse_param(raw, fields)?, sql_columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIELDS: &[CalcField] = &[
+        CalcField {
+            key: "amount_line",
+            label: "К выплате",
+        },
+        CalcField {
+            key: "qty",
+            label: "Количество",
+        },
+    ];
+    const COLUMNS: &[(&str, &str)] = &[("amount_line", "s.amount_line"), ("qty", "s.qty")];
+
+    #[test]
+    fn builds_sql_with_safe_division() {
+        let select = from_param("Цена=-amount_line / (qty - 1)", FIELDS, COLUMNS).unwrap();
+        assert_eq!(select.names, vec!["Цена".to_string()]);
+        assert_eq!(
+            select.exprs[0],
+            "(CAST((-s.amount_line) AS REAL) / NULLIF((s.qty - 1.0), 0))"
+        );
+        assert_eq!(
+            select.select_sql(),
+            format!(", CAST({} AS REAL) AS calc_0", select.exprs[0])
+        );
+        assert!(from_param("", FIELDS, COLUMNS).unwrap().exprs.is_empty());
+        assert!(from_param("x=amount_line", FIELDS, &[]).is_err());
+    }
+}
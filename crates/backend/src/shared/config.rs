@@ -5,6 +5,10 @@ use std::sync::OnceLock;
 static EXT_API_KEY: OnceLock<String> = OnceLock::new();
 static SCHEDULER_CONFIG_ENABLED: OnceLock<bool> = OnceLock::new();
 static MAIL_CONFIG: OnceLock<MailConfig> = OnceLock::new();
+static TELEGRAM_CONFIG: OnceLock<TelegramConfig> = OnceLock::new();
+static SANDBOX_CONFIG: OnceLock<SandboxConfig> = OnceLock::new();
+static HTTP_RETRY_CONFIG: OnceLock<HttpRetryConfig> = OnceLock::new();
+static ENVIRONMENT_CONFIG: OnceLock<EnvironmentConfig> = OnceLock::new();
 
 /// Store the mail configuration once at application startup, so LLM mail tools
 /// (which run without access to the loaded `Config`) can read it.
@@ -20,6 +24,58 @@ pub fn get_mail_config() -> &'static MailConfig {
         .unwrap_or_else(|| DISABLED.get_or_init(MailConfig::default))
 }
 
+/// Store the Telegram bot configuration once at application startup.
+pub fn set_telegram_config(cfg: TelegramConfig) {
+    let _ = TELEGRAM_CONFIG.set(cfg);
+}
+
+/// Returns the configured Telegram bot, or a disabled default if never set.
+pub fn get_telegram_config() -> &'static TelegramConfig {
+    static DISABLED: OnceLock<TelegramConfig> = OnceLock::new();
+    TELEGRAM_CONFIG
+        .get()
+        .unwrap_or_else(|| DISABLED.get_or_init(TelegramConfig::default))
+}
+
+/// Store the marketplace sandbox configuration once at application startup.
+pub fn set_sandbox_config(cfg: SandboxConfig) {
+    let _ = SANDBOX_CONFIG.set(cfg);
+}
+
+/// Returns the sandbox settings, or an empty default (no mock server) if never set.
+pub fn get_sandbox_config() -> &'static SandboxConfig {
+    static EMPTY: OnceLock<SandboxConfig> = OnceLock::new();
+    SANDBOX_CONFIG
+        .get()
+        .unwrap_or_else(|| EMPTY.get_or_init(SandboxConfig::default))
+}
+
+/// Store the marketplace API retry policy once at application startup.
+pub fn set_http_retry_config(cfg: HttpRetryConfig) {
+    let _ = HTTP_RETRY_CONFIG.set(cfg);
+}
+
+/// Returns the retry policy for marketplace API fetchers, or defaults if never set.
+pub fn get_http_retry_config() -> &'static HttpRetryConfig {
+    static DEFAULTS: OnceLock<HttpRetryConfig> = OnceLock::new();
+    HTTP_RETRY_CONFIG
+        .get()
+        .unwrap_or_else(|| DEFAULTS.get_or_init(HttpRetryConfig::default))
+}
+
+/// Store the environment mode once at application startup.
+pub fn set_environment_config(cfg: EnvironmentConfig) {
+    let _ = ENVIRONMENT_CONFIG.set(cfg);
+}
+
+/// Returns the environment settings, or `dev` if never set.
+pub fn get_environment_config() -> &'static EnvironmentConfig {
+    static DEV: OnceLock<EnvironmentConfig> = OnceLock::new();
+    ENVIRONMENT_CONFIG
+        .get()
+        .unwrap_or_else(|| DEV.get_or_init(EnvironmentConfig::default))
+}
+
 /// Set the external API key once at application startup.
 pub fn set_ext_api_key(key: String) {
     let _ = EXT_API_KEY.set(key);
@@ -74,7 +130,110 @@ pub struct Config {
     #[serde(default)]
     pub s3: S3Config,
     #[serde(default)]
+    pub ocr: OcrConfig,
+    #[serde(default)]
     pub mail: MailConfig,
+    #[serde(default)]
+    pub telegram: TelegramConfig,
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+    #[serde(default)]
+    pub http_retry: HttpRetryConfig,
+    #[serde(default)]
+    pub environment: EnvironmentConfig,
+    #[serde(default)]
+    pub secrets: SecretsConfig,
+}
+
+/// Ключ шифрования секретов в БД (API-ключи подключений a006).
+/// Хранится только в config.toml; пустой ключ — токены пишутся открытым текстом.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SecretsConfig {
+    /// Произвольная строка-пароль; ключ AES-256 получается из неё через SHA-256.
+    /// После шифрования данных менять нельзя — старые токены не расшифруются.
+    #[serde(default)]
+    pub encryption_key: String,
+}
+
+/// Режим окружения: баннер в шапке и подтверждение опасных операций
+/// (в staging/prod — токен, в prod — ещё и ручной ввод `prod`).
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct EnvironmentConfig {
+    #[serde(default)]
+    pub mode: contracts::system::environment::EnvironmentMode,
+    /// Подпись стенда в баннере.
+    #[serde(default)]
+    pub name: String,
+}
+
+/// Песочница для подключений в тестовом режиме (`ConnectionMP::is_sandbox`).
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SandboxConfig {
+    /// Базовый URL mock-сервера. Если задан, все запросы тестовых подключений
+    /// уходят на `{mock_base_url}/{host}{path}`; иначе — на sandbox-хосты WB
+    /// (для Ozon и Яндекс.Маркета песочницы нет — без mock-сервера запросы отклоняются).
+    #[serde(default)]
+    pub mock_base_url: String,
+}
+
+/// Повторы запросов к API маркетплейсов при 429, 5xx, таймауте и обрыве связи
+/// (см. `shared::marketplaces::retry`).
+#[derive(Debug, Deserialize, Clone)]
+pub struct HttpRetryConfig {
+    /// Всего попыток на один запрос, включая первую; 1 — без повторов.
+    #[serde(default = "default_http_retry_max_attempts")]
+    pub max_attempts: u32,
+    /// Пауза перед первым повтором, дальше удваивается (со случайным разбросом).
+    #[serde(default = "default_http_retry_base_delay_ms")]
+    pub base_delay_ms: u64,
+    /// Потолок паузы экспоненциального роста.
+    #[serde(default = "default_http_retry_max_delay_secs")]
+    pub max_delay_secs: u64,
+    /// Если сервер в Retry-After просит ждать дольше — не ждём, а отдаём ответ
+    /// вызывающему коду (задача завершится и повторится следующим запуском).
+    #[serde(default = "default_http_retry_max_retry_after_secs")]
+    pub max_retry_after_secs: u64,
+}
+
+impl Default for HttpRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_http_retry_max_attempts(),
+            base_delay_ms: default_http_retry_base_delay_ms(),
+            max_delay_secs: default_http_retry_max_delay_secs(),
+            max_retry_after_secs: default_http_retry_max_retry_after_secs(),
+        }
+    }
+}
+
+fn default_http_retry_max_attempts() -> u32 {
+    4
+}
+fn default_http_retry_base_delay_ms() -> u64 {
+    2_000
+}
+fn default_http_retry_max_delay_secs() -> u64 {
+    60
+}
+fn default_http_retry_max_retry_after_secs() -> u64 {
+    300
+}
+
+/// Telegram-бот для уведомлений пользователей (канал «Telegram»).
+/// Токен хранится только в config.toml.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct TelegramConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub bot_token: String,
+}
+
+impl TelegramConfig {
+    /// Бот включён и токен задан.
+    pub fn is_ready(&self) -> bool {
+        self.enabled && !self.bot_token.trim().is_empty()
+    }
 }
 
 /// Настройки почтового ящика для LLM (приём по IMAP, отправка по SMTP).
@@ -242,6 +401,64 @@ fn default_s3_max_upload_mb() -> u64 {
     512
 }
 
+/// Распознавание текста вложений S3 (task030). Текстовые файлы индексируются всегда;
+/// сканы и PDF — внешними утилитами выбранного движка.
+#[derive(Debug, Deserialize, Clone)]
+pub struct OcrConfig {
+    /// `"none"` — только текстовые файлы; `"tesseract"` — изображения через tesseract,
+    /// PDF через pdftotext (текстовый слой)
+    #[serde(default = "default_ocr_engine")]
+    pub engine: String,
+    #[serde(default = "default_ocr_tesseract_command")]
+    pub tesseract_command: String,
+    /// Языки tesseract (`-l`)
+    #[serde(default = "default_ocr_languages")]
+    pub languages: String,
+    #[serde(default = "default_ocr_pdftotext_command")]
+    pub pdftotext_command: String,
+    /// Файлы больше лимита не распознаются
+    #[serde(default = "default_ocr_max_file_mb")]
+    pub max_file_mb: u64,
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        Self {
+            engine: default_ocr_engine(),
+            tesseract_command: default_ocr_tesseract_command(),
+            languages: default_ocr_languages(),
+            pdftotext_command: default_ocr_pdftotext_command(),
+            max_file_mb: default_ocr_max_file_mb(),
+        }
+    }
+}
+
+impl OcrConfig {
+    pub fn max_file_bytes(&self) -> u64 {
+        self.max_file_mb.saturating_mul(1024).saturating_mul(1024)
+    }
+}
+
+fn default_ocr_engine() -> String {
+    "none".to_string()
+}
+
+fn default_ocr_tesseract_command() -> String {
+    "tesseract".to_string()
+}
+
+fn default_ocr_languages() -> String {
+    "rus+eng".to_string()
+}
+
+fn default_ocr_pdftotext_command() -> String {
+    "pdftotext".to_string()
+}
+
+fn default_ocr_max_file_mb() -> u64 {
+    50
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct LlmConfig {
     /// Путь к директории с MD-файлами базы знаний (Obsidian-формат).
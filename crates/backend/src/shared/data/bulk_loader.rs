@@ -0,0 +1,182 @@
+//! Массовая загрузка строк при полной пересборке проекций.
+//!
+//! Пересборка по строке на INSERT и по транзакции на документ/день за год данных
+//! тянется часами. Загрузчик копит строки и пишет их пачками multi-row INSERT
+//! в промежуточную таблицу `<table>_bulk_staging` — копию колонок без индексов
+//! и ограничений (аналог COPY, которого в SQLite нет). Запись идёт вне
+//! транзакции и не держит write-lock надолго; рабочая таблица не меняется.
+//!
+//! В конце [`StagedRows::move_into_with_conn`] в одной транзакции с удалением
+//! заменяемых строк переносит загруженное одним `INSERT ... SELECT` и удаляет
+//! стейджинг. До фиксации читатели видят старые строки периода.
+//!
+//! Стейджинг — обычная таблица, не TEMP: TEMP живёт в одном соединении, а пул
+//! отдаёт разные. Поэтому одну таблицу нельзя пересобирать двумя загрузками
+//! одновременно — вторая пересоздаст стейджинг первой.
+
+use std::marker::PhantomData;
+
+use anyhow::Result;
+use sea_orm::sea_query::Alias;
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, EntityName, EntityTrait, IntoActiveModel, Iterable,
+    QueryTrait,
+};
+
+use super::db::get_connection;
+
+/// Лимит параметров одного запроса SQLite (`SQLITE_MAX_VARIABLE_NUMBER`).
+const SQLITE_MAX_VARIABLES: usize = 32_766;
+/// Больше строк в пачке выигрыша не дают, а память держат.
+const MAX_CHUNK_ROWS: usize = 1_000;
+
+/// Строк в одном INSERT для таблицы с `columns` колонками.
+fn chunk_rows(columns: usize) -> usize {
+    (SQLITE_MAX_VARIABLES / columns.max(1)).clamp(1, MAX_CHUNK_ROWS)
+}
+
+fn staging_name(table: &str) -> String {
+    format!("{table}_bulk_staging")
+}
+
+/// Загрузка строк сущности `E` в стейджинг её таблицы.
+pub struct BulkLoader<E: EntityTrait> {
+    table: String,
+    staging: String,
+    chunk_rows: usize,
+    pending: Vec<E::Model>,
+    staged: u64,
+    _entity: PhantomData<E>,
+}
+
+impl<E> BulkLoader<E>
+where
+    E: EntityTrait,
+    E::Model: IntoActiveModel<E::ActiveModel>,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+{
+    /// Создаёт пустой стейджинг (остаток прерванной загрузки удаляется).
+    pub async fn begin() -> Result<Self> {
+        let table = E::default().table_name().to_string();
+        let staging = staging_name(&table);
+        let db = get_connection();
+        db.execute_unprepared(&format!("DROP TABLE IF EXISTS {staging}"))
+            .await?;
+        db.execute_unprepared(&format!(
+            "CREATE TABLE {staging} AS SELECT * FROM {table} WHERE 0"
+        ))
+        .await?;
+        Ok(Self {
+            table,
+            staging,
+            chunk_rows: chunk_rows(E::Column::iter().count()),
+            pending: Vec::new(),
+            staged: 0,
+            _entity: PhantomData,
+        })
+    }
+
+    pub async fn push(&mut self, row: E::Model) -> Result<()> {
+        self.pending.push(row);
+        if self.pending.len() >= self.chunk_rows {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn extend(&mut self, rows: impl IntoIterator<Item = E::Model>) -> Result<()> {
+        for row in rows {
+            self.push(row).await?;
+        }
+        Ok(())
+    }
+
+    /// Строк принято (включая ещё не записанные).
+    pub fn len(&self) -> u64 {
+        self.staged + self.pending.len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let rows = std::mem::take(&mut self.pending);
+        let count = rows.len() as u64;
+        let mut insert =
+            E::insert_many(rows.into_iter().map(IntoActiveModel::into_active_model)).into_query();
+        insert.into_table(Alias::new(self.staging.as_str()));
+        let db = get_connection();
+        db.execute(db.get_database_backend().build(&insert)).await?;
+        self.staged += count;
+        Ok(())
+    }
+
+    /// Дописывает остаток; дальше строки переносятся [`StagedRows::move_into_with_conn`].
+    pub async fn finish(mut self) -> Result<StagedRows> {
+        self.flush().await?;
+        Ok(StagedRows {
+            table: self.table,
+            staging: self.staging,
+            rows: self.staged,
+        })
+    }
+}
+
+/// Загруженный стейджинг, ждущий переноса в рабочую таблицу.
+pub struct StagedRows {
+    table: String,
+    staging: String,
+    pub rows: u64,
+}
+
+impl StagedRows {
+    /// Имя стейджинга — для подзапросов вида `registrator_ref IN (SELECT ... FROM staging)`.
+    pub fn staging_table(&self) -> &str {
+        &self.staging
+    }
+
+    /// Переносит строки в рабочую таблицу и удаляет стейджинг. Вызывается в той же
+    /// транзакции, что и удаление заменяемых строк; `OR REPLACE` — как у построчного
+    /// upsert, если строка с тем же ключом осталась вне удалённого диапазона.
+    pub async fn move_into_with_conn<C: ConnectionTrait>(&self, db: &C) -> Result<u64> {
+        let moved = db
+            .execute_unprepared(&format!(
+                "INSERT OR REPLACE INTO {} SELECT * FROM {}",
+                self.table, self.staging
+            ))
+            .await?
+            .rows_affected();
+        db.execute_unprepared(&format!("DROP TABLE {}", self.staging))
+            .await?;
+        Ok(moved)
+    }
+
+    /// Отказ от загрузки: стейджинг удаляется, рабочая таблица не меняется.
+    pub async fn discard(self) -> Result<()> {
+        get_connection()
+            .execute_unprepared(&format!("DROP TABLE IF EXISTS {}", self.staging))
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_fits_sqlite_variable_limit() {
+        assert_eq!(chunk_rows(45), 728);
+        assert_eq!(chunk_rows(17), MAX_CHUNK_ROWS);
+        assert_eq!(chunk_rows(0), MAX_CHUNK_ROWS);
+        assert_eq!(chunk_rows(40_000), 1);
+        assert_eq!(
+            staging_name("p904_sales_data"),
+            "p904_sales_data_bulk_staging"
+        );
+    }
+}
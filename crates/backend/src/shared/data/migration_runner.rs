@@ -1,7 +1,9 @@
 use crate::shared::config;
+use crate::shared::data::online_migrations::{self, MigrationCompat};
 use sha2::{Digest, Sha384};
 use sqlx::sqlite::SqlitePool;
 use sqlx::{Executor, Row};
+use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 
 fn build_sqlite_url(path: &Path) -> String {
@@ -175,8 +177,13 @@ pub async fn run_migrations() -> anyhow::Result<()> {
     repair_line_ending_checksums(&pool, &migrations_dir).await?;
     repair_known_legacy_checksums(&pool, &migrations_dir).await?;
 
-    let migrator = sqlx::migrate::Migrator::new(migrations_dir.as_path()).await?;
+    let mut migrator = sqlx::migrate::Migrator::new(migrations_dir.as_path()).await?;
+    if check_schema_compatibility(&pool, &migrator).await? {
+        // Схему уже расширила более новая сборка (rolling deploy) — её версии не наши
+        migrator.set_ignore_missing(true);
+    }
     migrator.run(&pool).await?;
+    record_migration_compat(&pool, &migrator).await?;
 
     ensure_a015_dealer_price_ut_column(&pool).await?;
     ensure_llm_chat_agent_fk_to_a038(&pool).await?;
@@ -185,6 +192,77 @@ pub async fn run_migrations() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Стартовая проверка схемы: если в БД применены миграции новее этой сборки, все они
+/// должны быть помечены `expand` в `sys_migration_compat`, иначе старт отклоняется.
+/// Возвращает `true`, если такие (совместимые) версии есть.
+async fn check_schema_compatibility(
+    pool: &SqlitePool,
+    migrator: &sqlx::migrate::Migrator,
+) -> anyhow::Result<bool> {
+    if !has_table(pool, "_sqlx_migrations").await? {
+        return Ok(false);
+    }
+    let applied: Vec<i64> = sqlx::query_scalar(
+        "SELECT version FROM _sqlx_migrations WHERE success = 1 ORDER BY version",
+    )
+    .fetch_all(pool)
+    .await?;
+    let known: BTreeSet<i64> = migrator.iter().map(|m| m.version).collect();
+    let mut recorded = HashMap::new();
+    if has_table(pool, "sys_migration_compat").await? {
+        for row in sqlx::query("SELECT version, compat FROM sys_migration_compat")
+            .fetch_all(pool)
+            .await?
+        {
+            let compat: String = row.try_get("compat")?;
+            if let Some(compat) = MigrationCompat::from_code(&compat) {
+                recorded.insert(row.try_get::<i64, _>("version")?, compat);
+            }
+        }
+    }
+
+    let check = online_migrations::check_unknown_versions(&applied, &known, &recorded);
+    if !check.incompatible.is_empty() {
+        anyhow::bail!(
+            "Database schema is newer than this build and not backward compatible \
+             (migrations {:?} are not marked `-- compat: expand`). \
+             Refusing to start: deploy the build that applied them or newer.",
+            check.incompatible
+        );
+    }
+    if !check.unknown.is_empty() {
+        tracing::warn!(
+            "Database has expand-only migrations from a newer build: {:?}. Starting in compatibility mode.",
+            check.unknown
+        );
+    }
+    Ok(!check.unknown.is_empty())
+}
+
+/// Записывает флаги совместимости миграций этой сборки, чтобы их видели
+/// предыдущие сборки при старте на той же БД.
+async fn record_migration_compat(
+    pool: &SqlitePool,
+    migrator: &sqlx::migrate::Migrator,
+) -> anyhow::Result<()> {
+    if !has_table(pool, "sys_migration_compat").await? {
+        return Ok(());
+    }
+    let mut tx = pool.begin().await?;
+    for migration in migrator.iter() {
+        sqlx::query(
+            "INSERT INTO sys_migration_compat (version, compat) VALUES (?1, ?2)
+             ON CONFLICT(version) DO UPDATE SET compat = excluded.compat",
+        )
+        .bind(migration.version)
+        .bind(online_migrations::parse_compat(&migration.sql).code())
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
 /// Текущий номер (наибольшая успешно применённая версия) миграции БД этого инстанса —
 /// для ручной сверки с `PluginManifest.built_for_migration` на странице разработки плагина.
 pub async fn current_migration_version() -> anyhow::Result<i64> {
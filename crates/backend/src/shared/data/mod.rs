@@ -1,3 +1,9 @@
+pub mod bulk_loader;
 pub mod db;
 pub mod migration_runner;
+pub mod online_migrations;
+pub mod projection_archive;
+pub mod projection_compaction;
+pub mod projection_snapshots;
 pub mod raw_storage;
+pub mod unit_of_work;
@@ -0,0 +1,295 @@
+//! Онлайн-миграции (expand/contract) для rolling deploy без остановки бэкенда.
+//!
+//! Совместимость шага объявляется в первых строках файла миграции комментарием
+//! `-- compat: expand`: миграция только добавляет (таблицы, nullable-колонки, индексы,
+//! триггеры двойной записи), и предыдущая сборка продолжает работать на новой схеме.
+//! Всё остальное — `contract` (удаление/переименование, NOT NULL без DEFAULT) —
+//! считается несовместимым; метка по умолчанию, если флага нет.
+//!
+//! Флаги применённых миграций записываются в `sys_migration_compat`. Сборка, которая
+//! видит в БД версии новее своих, стартует только если все они `expand`; иначе старт
+//! отклоняется, чтобы старый код не писал в несовместимую схему.
+//!
+//! Переименование «горячей» колонки делается в три релиза:
+//! 1. expand: [`DualWriteColumn::expand_sql`] — новая колонка, бэкфилл и триггеры,
+//!    синхронизирующие обе колонки при записи любой сборкой;
+//! 2. код читает и пишет новую колонку;
+//! 3. contract (когда старых инстансов не осталось): [`DualWriteColumn::contract_sql`].
+
+use std::collections::{BTreeSet, HashMap};
+
+/// Совместимость шага миграции с предыдущей сборкой.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationCompat {
+    /// Обратно совместимый шаг: старый код работает на новой схеме
+    Expand,
+    /// Несовместимый шаг: старый код на этой схеме запускать нельзя
+    Contract,
+}
+
+impl MigrationCompat {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Expand => "expand",
+            Self::Contract => "contract",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim() {
+            "expand" => Some(Self::Expand),
+            "contract" => Some(Self::Contract),
+            _ => None,
+        }
+    }
+}
+
+/// Сколько строк заголовка миграции просматривается в поисках флага.
+const HEADER_LINES: usize = 10;
+
+/// Первая версия, с которой каждая миграция обязана объявлять флаг совместимости.
+pub const FIRST_FLAGGED_VERSION: i64 = 197;
+
+/// Флаг совместимости, явно объявленный в заголовке миграции.
+pub fn declared_compat(sql: &str) -> Option<MigrationCompat> {
+    sql.lines()
+        .take(HEADER_LINES)
+        .filter_map(|line| line.trim().strip_prefix("--"))
+        .filter_map(|comment| comment.trim().strip_prefix("compat:"))
+        .find_map(MigrationCompat::from_code)
+}
+
+/// Флаг совместимости из заголовка миграции (`-- compat: expand`); без флага — `Contract`.
+pub fn parse_compat(sql: &str) -> MigrationCompat {
+    declared_compat(sql).unwrap_or(MigrationCompat::Contract)
+}
+
+/// Применённые в БД версии, которых нет в этой сборке, и несовместимые среди них.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct UnknownVersions {
+    pub unknown: Vec<i64>,
+    /// Версии `contract` или без записи о совместимости
+    pub incompatible: Vec<i64>,
+}
+
+/// Сверяет применённые версии с известными сборке. Версия без записи в
+/// `sys_migration_compat` считается несовместимой.
+pub fn check_unknown_versions(
+    applied: &[i64],
+    known: &BTreeSet<i64>,
+    recorded: &HashMap<i64, MigrationCompat>,
+) -> UnknownVersions {
+    let mut result = UnknownVersions::default();
+    for version in applied.iter().copied().filter(|v| !known.contains(v)) {
+        result.unknown.push(version);
+        if recorded.get(&version) != Some(&MigrationCompat::Expand) {
+            result.incompatible.push(version);
+        }
+    }
+    result
+}
+
+/// Переименование колонки через двойную запись. Таблица должна быть rowid-таблицей
+/// (не `WITHOUT ROWID`); `column_type` — тип новой колонки (nullable).
+#[derive(Debug, Clone, Copy)]
+pub struct DualWriteColumn<'a> {
+    pub table: &'a str,
+    pub old_column: &'a str,
+    pub new_column: &'a str,
+    pub column_type: &'a str,
+}
+
+impl DualWriteColumn<'_> {
+    fn trigger_name(&self, suffix: &str) -> String {
+        format!(
+            "trg_{}_dw_{}_{}_{}",
+            self.table, self.old_column, self.new_column, suffix
+        )
+    }
+
+    /// SQL expand-шага: колонка, бэкфилл и триггеры, копирующие значение в обе стороны.
+    /// Рекурсивные триггеры в SQLite выключены, а условия `WHEN` не дают циклов и при них.
+    pub fn expand_sql(&self) -> String {
+        let Self {
+            table,
+            old_column: old,
+            new_column: new,
+            column_type,
+        } = *self;
+        format!(
+            "ALTER TABLE {table} ADD COLUMN {new} {column_type};\n\
+             UPDATE {table} SET {new} = {old} WHERE {new} IS NULL;\n\
+             CREATE TRIGGER IF NOT EXISTS {ins} AFTER INSERT ON {table}\n\
+             WHEN NEW.{new} IS NULL OR NEW.{old} IS NULL\n\
+             BEGIN\n\
+             \x20   UPDATE {table} SET {new} = COALESCE(NEW.{new}, NEW.{old}),\n\
+             \x20       {old} = COALESCE(NEW.{old}, NEW.{new})\n\
+             \x20   WHERE rowid = NEW.rowid;\n\
+             END;\n\
+             CREATE TRIGGER IF NOT EXISTS {upd_old} AFTER UPDATE OF {old} ON {table}\n\
+             WHEN NEW.{old} IS NOT OLD.{old} AND NEW.{new} IS OLD.{new}\n\
+             BEGIN\n\
+             \x20   UPDATE {table} SET {new} = NEW.{old} WHERE rowid = NEW.rowid;\n\
+             END;\n\
+             CREATE TRIGGER IF NOT EXISTS {upd_new} AFTER UPDATE OF {new} ON {table}\n\
+             WHEN NEW.{new} IS NOT OLD.{new} AND NEW.{old} IS OLD.{old}\n\
+             BEGIN\n\
+             \x20   UPDATE {table} SET {old} = NEW.{new} WHERE rowid = NEW.rowid;\n\
+             END;\n",
+            ins = self.trigger_name("ins"),
+            upd_old = self.trigger_name("upd_old"),
+            upd_new = self.trigger_name("upd_new"),
+        )
+    }
+
+    /// SQL contract-шага: снять триггеры и удалить старую колонку
+    /// (`DROP COLUMN` — SQLite 3.35+; колонка не должна входить в индексы).
+    pub fn contract_sql(&self) -> String {
+        format!(
+            "DROP TRIGGER IF EXISTS {};\n\
+             DROP TRIGGER IF EXISTS {};\n\
+             DROP TRIGGER IF EXISTS {};\n\
+             ALTER TABLE {} DROP COLUMN {};\n",
+            self.trigger_name("ins"),
+            self.trigger_name("upd_old"),
+            self.trigger_name("upd_new"),
+            self.table,
+            self.old_column
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::{Executor, Row};
+
+    #[test]
+    fn compat_flag_is_read_from_header() {
+        assert_eq!(
+            parse_compat("-- Новая таблица\n-- compat: expand\nCREATE TABLE t (id TEXT);"),
+            MigrationCompat::Expand
+        );
+        assert_eq!(
+            parse_compat("--compat: contract\nDROP TABLE t;"),
+            MigrationCompat::Contract
+        );
+        assert_eq!(
+            parse_compat("CREATE TABLE t (id TEXT);"),
+            MigrationCompat::Contract
+        );
+        let late = format!("{}-- compat: expand\n", "SELECT 1;\n".repeat(HEADER_LINES));
+        assert_eq!(parse_compat(&late), MigrationCompat::Contract);
+    }
+
+    #[test]
+    fn new_migrations_declare_compat_flag() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../migrations");
+        let mut unflagged = Vec::new();
+        for entry in std::fs::read_dir(&dir).expect("migrations dir") {
+            let path = entry.expect("migration entry").path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Some(version) = name.split('_').next().and_then(|v| v.parse::<i64>().ok()) else {
+                continue;
+            };
+            if !name.ends_with(".sql") || version < FIRST_FLAGGED_VERSION {
+                continue;
+            }
+            let sql = std::fs::read_to_string(&path).expect("migration file");
+            if declared_compat(&sql).is_none() {
+                unflagged.push(name.to_string());
+            }
+        }
+        unflagged.sort();
+        assert!(
+            unflagged.is_empty(),
+            "migrations without `-- compat:` header: {:?}",
+            unflagged
+        );
+    }
+
+    #[test]
+    fn unknown_versions_must_all_be_expand() {
+        let known: BTreeSet<i64> = [1, 2, 3].into_iter().collect();
+        let mut recorded = HashMap::new();
+        recorded.insert(4, MigrationCompat::Expand);
+        recorded.insert(5, MigrationCompat::Contract);
+
+        assert_eq!(
+            check_unknown_versions(&[1, 2, 3], &known, &recorded),
+            UnknownVersions::default()
+        );
+        let only_expand = check_unknown_versions(&[1, 2, 3, 4], &known, &recorded);
+        assert_eq!(only_expand.unknown, vec![4]);
+        assert!(only_expand.incompatible.is_empty());
+
+        let mixed = check_unknown_versions(&[1, 2, 3, 4, 5, 6], &known, &recorded);
+        assert_eq!(mixed.unknown, vec![4, 5, 6]);
+        assert_eq!(mixed.incompatible, vec![5, 6]);
+    }
+
+    #[tokio::test]
+    async fn dual_write_keeps_both_columns_in_sync() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        pool.execute(
+            "CREATE TABLE t (id TEXT PRIMARY KEY, qty REAL); INSERT INTO t VALUES ('a', 1);",
+        )
+        .await
+        .unwrap();
+        let rename = DualWriteColumn {
+            table: "t",
+            old_column: "qty",
+            new_column: "quantity",
+            column_type: "REAL",
+        };
+        pool.execute(rename.expand_sql().as_str()).await.unwrap();
+
+        let pair = |id: &'static str| {
+            let pool = pool.clone();
+            async move {
+                let row = sqlx::query("SELECT qty, quantity FROM t WHERE id = ?1")
+                    .bind(id)
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap();
+                (
+                    row.get::<Option<f64>, _>("qty"),
+                    row.get::<Option<f64>, _>("quantity"),
+                )
+            }
+        };
+
+        // Бэкфилл существующих строк
+        assert_eq!(pair("a").await, (Some(1.0), Some(1.0)));
+        // Старая сборка пишет только старую колонку
+        pool.execute("INSERT INTO t (id, qty) VALUES ('b', 2)")
+            .await
+            .unwrap();
+        pool.execute("UPDATE t SET qty = 3 WHERE id = 'a'")
+            .await
+            .unwrap();
+        assert_eq!(pair("b").await, (Some(2.0), Some(2.0)));
+        assert_eq!(pair("a").await, (Some(3.0), Some(3.0)));
+        // Новая сборка пишет только новую колонку
+        pool.execute("INSERT INTO t (id, quantity) VALUES ('c', 4)")
+            .await
+            .unwrap();
+        pool.execute("UPDATE t SET quantity = 5 WHERE id = 'b'")
+            .await
+            .unwrap();
+        assert_eq!(pair("c").await, (Some(4.0), Some(4.0)));
+        assert_eq!(pair("b").await, (Some(5.0), Some(5.0)));
+
+        pool.execute(rename.contract_sql().as_str()).await.unwrap();
+        pool.execute("INSERT INTO t (id, quantity) VALUES ('d', 6)")
+            .await
+            .unwrap();
+    }
+}
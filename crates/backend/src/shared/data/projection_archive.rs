@@ -0,0 +1,495 @@
+//! Архивация больших проекций и документов по месяцам.
+//!
+//! SQLite не умеет партиционировать таблицы, поэтому «партиции» реализованы
+//! переносом: строки старше границы архивации (`YYYY-MM`, хранится в `sys_settings`)
+//! перекладываются из рабочей таблицы в `*_archive` с тем же набором колонок.
+//! Рабочая таблица остаётся маленькой, и запросы за «горячие» месяцы не сканируют
+//! годы истории.
+//!
+//! Инвариант: в архиве лежат только строки раньше границы, а граница никогда не
+//! сдвигается назад. Поэтому запрос, период которого начинается не раньше границы,
+//! читает только рабочую таблицу; иначе репозиторий прозрачно подставляет
+//! `UNION ALL` рабочей и архивной таблиц (см. [`source`], [`route_select`]).
+//! Произвольный SQL (LLM, плагины) получает архив через CTE, см. [`with_archive`].
+//! Перепроведение документа из архивного месяца удаляет его строки из обеих таблиц
+//! и пишет новые в рабочую — это корректно, т.к. рабочая таблица читается всегда.
+//!
+//! Документы (`a012_wb_sales`) архивируются по дате продажи. Чтение по id и другим
+//! ключам без периода идёт через [`source`] с `date_from = None`, т.е. с архивом.
+//! Перед изменением документ возвращается в рабочую таблицу ([`restore_with_conn`]),
+//! поэтому запись всегда идёт в рабочую таблицу. Исключение — массовые правки
+//! реквизитов, не влияющих на дату (переназначение организации, описания): они
+//! меняют строки на месте в обеих таблицах ([`physical_tables`]).
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+use anyhow::{bail, Result};
+use chrono::NaiveDate;
+use contracts::system::projection_archive::{
+    ArchiveMoveResultDto, ArchiveMovedTableDto, ArchivePartitionDto, ArchiveStatusDto,
+    ArchiveTableDto,
+};
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, EntityTrait, FromQueryResult, QueryTrait, Select,
+    SelectModel, SelectorRaw, Statement, TransactionTrait,
+};
+
+use super::db::get_connection;
+
+/// Таблица, которая умеет жить в рабочей и архивной таблицах.
+struct ArchiveTarget {
+    table: &'static str,
+    archive_table: &'static str,
+    /// Выражение месяца строки (`YYYY-MM`).
+    month_expr: &'static str,
+    /// Выражение даты строки (`YYYY-MM-DD`).
+    date_expr: &'static str,
+}
+
+const TARGETS: &[ArchiveTarget] = &[
+    ArchiveTarget {
+        table: "p900_sales_register",
+        archive_table: "p900_sales_register_archive",
+        month_expr: "substr(sale_date, 1, 7)",
+        date_expr: "substr(sale_date, 1, 10)",
+    },
+    ArchiveTarget {
+        table: "p904_sales_data",
+        archive_table: "p904_sales_data_archive",
+        month_expr: "substr(date, 1, 7)",
+        date_expr: "substr(date, 1, 10)",
+    },
+    ArchiveTarget {
+        table: "a012_wb_sales",
+        archive_table: "a012_wb_sales_archive",
+        month_expr: "substr(sale_date, 1, 7)",
+        date_expr: "substr(sale_date, 1, 10)",
+    },
+];
+
+/// Таблицы, в которые пишет перенос в архив.
+pub const ARCHIVE_TABLES: &[&str] = &[
+    "p900_sales_register",
+    "p900_sales_register_archive",
+    "p904_sales_data",
+    "p904_sales_data_archive",
+    "a012_wb_sales",
+    "a012_wb_sales_archive",
+];
+
+/// Кэш границы архивации: маршрутизация запросов синхронная и не ходит в `sys_settings`.
+static BOUNDARY: RwLock<Option<String>> = RwLock::new(None);
+
+fn target(table: &str) -> Option<&'static ArchiveTarget> {
+    TARGETS.iter().find(|t| t.table == table)
+}
+
+fn boundary() -> Option<String> {
+    BOUNDARY.read().map(|b| b.clone()).unwrap_or(None)
+}
+
+/// Обновляет кэш границы (после сдвига архива; в тестах — напрямую).
+pub(crate) fn set_cached_boundary(value: Option<String>) {
+    if let Ok(mut guard) = BOUNDARY.write() {
+        *guard = value;
+    }
+}
+
+/// Загружает границу архивации из настроек (вызывается при старте сервера).
+pub async fn load_boundary() -> Result<()> {
+    let value = crate::system::settings::service::get_projection_archive_boundary().await?;
+    set_cached_boundary(value);
+    Ok(())
+}
+
+/// Нужен ли архив запросу с началом периода `date_from` (`None` — период не ограничен).
+fn includes_archive(boundary: Option<&str>, date_from: Option<&str>) -> bool {
+    match (boundary, date_from) {
+        (None, _) => false,
+        (Some(_), None) => true,
+        (Some(b), Some(from)) => from < format!("{b}-01").as_str(),
+    }
+}
+
+/// Источник строк для `FROM`: рабочая таблица или её объединение с архивом.
+pub fn source(table: &str, date_from: Option<&str>) -> String {
+    match target(table) {
+        Some(t) if includes_archive(boundary().as_deref(), date_from) => format!(
+            "(SELECT * FROM {} UNION ALL SELECT * FROM {})",
+            t.table, t.archive_table
+        ),
+        _ => table.to_string(),
+    }
+}
+
+/// Физические таблицы `table`: рабочая и, если таблица архивируется, архивная.
+/// Для массовых правок, которые не меняют дату строки и поэтому идут на месте
+/// в обеих таблицах, без возврата документов из архива.
+pub fn physical_tables(table: &str) -> Vec<&str> {
+    match target(table) {
+        Some(t) => vec![t.table, t.archive_table],
+        None => vec![table],
+    }
+}
+
+/// Подключает архив к произвольному SELECT: для каждой архивируемой таблицы из
+/// `tables` добавляется CTE с её именем, который затеняет таблицу, а внутри CTE
+/// `main.<table>` по-прежнему указывает на рабочую таблицу.
+pub fn with_archive(sql: String, tables: &[String]) -> String {
+    if boundary().is_none() {
+        return sql;
+    }
+    let ctes: Vec<String> = tables
+        .iter()
+        .filter_map(|table| target(table))
+        .map(|t| {
+            format!(
+                "{table} AS (SELECT * FROM main.{table} UNION ALL SELECT * FROM main.{archive})",
+                table = t.table,
+                archive = t.archive_table
+            )
+        })
+        .collect();
+    if ctes.is_empty() {
+        return sql;
+    }
+    format!("WITH {} {sql}", ctes.join(", "))
+}
+
+/// Подменяет `FROM "table"` в запросе, собранном SeaORM, на [`source`] с тем же алиасом,
+/// чтобы квалифицированные колонки (`"table"."col"`) продолжали работать.
+pub fn route_statement(table: &str, date_from: Option<&str>, mut stmt: Statement) -> Statement {
+    let routed = source(table, date_from);
+    if routed != table {
+        let from = format!("FROM \"{table}\"");
+        stmt.sql = stmt
+            .sql
+            .replacen(&from, &format!("FROM {routed} AS \"{table}\""), 1);
+    }
+    stmt
+}
+
+/// Выполняет `Select` сущности с учётом архива.
+pub fn route_select<E>(
+    table: &str,
+    date_from: Option<&str>,
+    select: Select<E>,
+) -> SelectorRaw<SelectModel<E::Model>>
+where
+    E: EntityTrait,
+    E::Model: FromQueryResult,
+{
+    let stmt = route_statement(table, date_from, select.build(DatabaseBackend::Sqlite));
+    E::find().from_raw_sql(stmt)
+}
+
+/// `COUNT(*)` по `Select` сущности с учётом архива.
+pub async fn count_routed<E: EntityTrait>(
+    table: &str,
+    date_from: Option<&str>,
+    select: Select<E>,
+) -> Result<u64> {
+    let stmt = route_statement(table, date_from, select.build(DatabaseBackend::Sqlite));
+    let count = Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        &format!("SELECT COUNT(*) AS num_items FROM ({}) AS sub", stmt.sql),
+        stmt.values.map(|v| v.0).unwrap_or_default(),
+    );
+    let row = get_connection().query_one(count).await?;
+    Ok(row
+        .and_then(|r| r.try_get::<i64>("", "num_items").ok())
+        .unwrap_or(0) as u64)
+}
+
+/// Удаляет архивные строки документа-регистратора (перед перепроведением).
+pub async fn delete_archived_by_registrator_with_conn<C: ConnectionTrait>(
+    db: &C,
+    table: &str,
+    registrator_ref: &str,
+) -> Result<u64> {
+    let Some(t) = target(table) else {
+        return Ok(0);
+    };
+    if boundary().is_none() {
+        return Ok(0);
+    }
+    let result = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            &format!("DELETE FROM {} WHERE registrator_ref = ?", t.archive_table),
+            vec![registrator_ref.into()],
+        ))
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Возвращает строку `id` из архива в рабочую таблицу перед её изменением.
+/// Вызывается в транзакции записи, поэтому строка не теряется и не двоится.
+pub async fn restore_with_conn<C: ConnectionTrait>(db: &C, table: &str, id: &str) -> Result<bool> {
+    let Some(t) = target(table) else {
+        return Ok(false);
+    };
+    if boundary().is_none() {
+        return Ok(false);
+    }
+    let moved = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            &format!(
+                "INSERT INTO {} SELECT * FROM {} WHERE id = ?",
+                t.table, t.archive_table
+            ),
+            vec![id.into()],
+        ))
+        .await?
+        .rows_affected();
+    if moved > 0 {
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            &format!("DELETE FROM {} WHERE id = ?", t.archive_table),
+            vec![id.into()],
+        ))
+        .await?;
+    }
+    Ok(moved > 0)
+}
+
+/// Удаляет строки периода из рабочей и архивной таблиц (пересборка проекции с нуля).
+pub async fn delete_period_with_conn<C: ConnectionTrait>(
+    db: &C,
+    table: &str,
+    date_from: &str,
+    date_to: &str,
+) -> Result<u64> {
+    let Some(t) = target(table) else {
+        bail!("Таблица {} не поддерживает архивацию", table);
+    };
+    let mut tables = vec![t.table];
+    if includes_archive(boundary().as_deref(), Some(date_from)) {
+        tables.push(t.archive_table);
+    }
+    let mut deleted = 0;
+    for name in tables {
+        let result = db
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                &format!(
+                    "DELETE FROM {} WHERE {} >= ? AND {} <= ?",
+                    name, t.date_expr, t.date_expr
+                ),
+                vec![date_from.into(), date_to.into()],
+            ))
+            .await?;
+        deleted += result.rows_affected();
+    }
+    Ok(deleted)
+}
+
+/// Удаляет из рабочей и архивной таблиц строки регистраторов, перечисленных
+/// в `registrator_ref` таблицы `staging` (массовая пересборка: документ мог
+/// сменить дату и лежать вне очищаемого периода).
+pub async fn delete_staged_registrators_with_conn<C: ConnectionTrait>(
+    db: &C,
+    table: &str,
+    staging: &str,
+) -> Result<u64> {
+    let Some(t) = target(table) else {
+        bail!("Таблица {} не поддерживает архивацию", table);
+    };
+    let mut tables = vec![t.table];
+    if boundary().is_some() {
+        tables.push(t.archive_table);
+    }
+    let mut deleted = 0;
+    for name in tables {
+        deleted += db
+            .execute_unprepared(&format!(
+                "DELETE FROM {name} WHERE registrator_ref IN \
+                 (SELECT DISTINCT registrator_ref FROM {staging})"
+            ))
+            .await?
+            .rows_affected();
+    }
+    Ok(deleted)
+}
+
+/// Проверяет формат месяца `YYYY-MM`.
+pub fn validate_month(month: &str) -> Result<()> {
+    if month.len() != 7 || NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d").is_err() {
+        bail!("Invalid month '{month}', expected YYYY-MM");
+    }
+    Ok(())
+}
+
+/// Переносит в архив все строки раньше `before_month`.
+///
+/// Граница сохраняется до переноса: если перенос упадёт, строки останутся в рабочей
+/// таблице, которая читается всегда, и инвариант не нарушится.
+pub async fn archive_before(before_month: &str) -> Result<ArchiveMoveResultDto> {
+    validate_month(before_month)?;
+    let current = crate::system::settings::service::get_projection_archive_boundary().await?;
+    let new_boundary = match current {
+        Some(c) if c.as_str() > before_month => c,
+        _ => before_month.to_string(),
+    };
+    crate::system::settings::service::set_projection_archive_boundary(&new_boundary).await?;
+    set_cached_boundary(Some(new_boundary.clone()));
+
+    let db = get_connection();
+    let txn = db.begin().await?;
+    let mut tables = Vec::new();
+    for t in TARGETS {
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            &format!(
+                "INSERT INTO {archive} SELECT * FROM {table} WHERE {month} < ?",
+                archive = t.archive_table,
+                table = t.table,
+                month = t.month_expr,
+            ),
+            vec![new_boundary.clone().into()],
+        ))
+        .await?;
+        let deleted = txn
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                &format!("DELETE FROM {} WHERE {} < ?", t.table, t.month_expr),
+                vec![new_boundary.clone().into()],
+            ))
+            .await?;
+        tables.push(ArchiveMovedTableDto {
+            table: t.table.to_string(),
+            moved_rows: deleted.rows_affected(),
+        });
+    }
+    txn.commit().await?;
+
+    tracing::info!(
+        "Projection archive: boundary {}, moved {:?}",
+        new_boundary,
+        tables
+            .iter()
+            .map(|t| (t.table.as_str(), t.moved_rows))
+            .collect::<Vec<_>>()
+    );
+
+    Ok(ArchiveMoveResultDto {
+        boundary: new_boundary,
+        tables,
+    })
+}
+
+async fn month_counts(table: &str, month_expr: &str) -> Result<Vec<(String, i64)>> {
+    let rows = get_connection()
+        .query_all(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            format!("SELECT {month_expr} AS month, COUNT(*) AS cnt FROM {table} GROUP BY 1"),
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            (
+                r.try_get::<Option<String>>("", "month")
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default(),
+                r.try_get::<i64>("", "cnt").unwrap_or(0),
+            )
+        })
+        .collect())
+}
+
+/// Размеры месячных партиций всех архивируемых таблиц.
+pub async fn status() -> Result<ArchiveStatusDto> {
+    let mut tables = Vec::new();
+    for t in TARGETS {
+        let mut months: BTreeMap<String, (i64, i64)> = BTreeMap::new();
+        for (month, cnt) in month_counts(t.table, t.month_expr).await? {
+            months.entry(month).or_default().0 += cnt;
+        }
+        for (month, cnt) in month_counts(t.archive_table, t.month_expr).await? {
+            months.entry(month).or_default().1 += cnt;
+        }
+        let partitions: Vec<ArchivePartitionDto> = months
+            .into_iter()
+            .rev()
+            .map(|(month, (hot_rows, archive_rows))| ArchivePartitionDto {
+                month,
+                hot_rows,
+                archive_rows,
+            })
+            .collect();
+        tables.push(ArchiveTableDto {
+            table: t.table.to_string(),
+            archive_table: t.archive_table.to_string(),
+            hot_rows: partitions.iter().map(|p| p.hot_rows).sum(),
+            archive_rows: partitions.iter().map(|p| p.archive_rows).sum(),
+            partitions,
+        });
+    }
+    Ok(ArchiveStatusDto {
+        boundary: crate::system::settings::service::get_projection_archive_boundary().await?,
+        tables,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_is_skipped_for_hot_periods() {
+        assert!(!includes_archive(None, None));
+        assert!(!includes_archive(Some("2025-01"), Some("2025-01-01")));
+        assert!(!includes_archive(
+            Some("2025-01"),
+            Some("2025-03-15T10:00:00")
+        ));
+    }
+
+    #[test]
+    fn archive_is_included_for_old_or_open_periods() {
+        assert!(includes_archive(Some("2025-01"), None));
+        assert!(includes_archive(Some("2025-01"), Some("2024-12-31")));
+    }
+
+    #[test]
+    fn route_statement_keeps_table_alias() {
+        set_cached_boundary(Some("2025-01".to_string()));
+        let stmt = Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT \"p904_sales_data\".\"id\" FROM \"p904_sales_data\" WHERE 1".to_string(),
+        );
+        let routed = route_statement("p904_sales_data", None, stmt);
+        assert_eq!(
+            routed.sql,
+            "SELECT \"p904_sales_data\".\"id\" FROM (SELECT * FROM p904_sales_data \
+             UNION ALL SELECT * FROM p904_sales_data_archive) AS \"p904_sales_data\" WHERE 1"
+        );
+    }
+
+    #[test]
+    fn with_archive_shadows_archived_tables_only() {
+        set_cached_boundary(Some("2025-01".to_string()));
+        let sql = with_archive(
+            "SELECT * FROM a012_wb_sales s JOIN a004_nomenclature n ON n.id = s.nomenclature_ref"
+                .to_string(),
+            &["a004_nomenclature".to_string(), "a012_wb_sales".to_string()],
+        );
+        assert_eq!(
+            sql,
+            "WITH a012_wb_sales AS (SELECT * FROM main.a012_wb_sales \
+             UNION ALL SELECT * FROM main.a012_wb_sales_archive) \
+             SELECT * FROM a012_wb_sales s JOIN a004_nomenclature n ON n.id = s.nomenclature_ref"
+        );
+    }
+
+    #[test]
+    fn month_validation() {
+        assert!(validate_month("2025-01").is_ok());
+        assert!(validate_month("2025-13").is_err());
+        assert!(validate_month("2025-1").is_err());
+    }
+}
@@ -0,0 +1,248 @@
+//! Компактизация проекций: удаление строк, чей регистратор больше не проведён.
+//!
+//! Проекции пересобираются по `registrator_ref` при каждом проведении, но строки
+//! остаются, если документ потом помечен на удаление, распроведён в обход сервиса
+//! или удалён из таблицы агрегата. Такие «мёртвые» строки искажают отчёты и
+//! раздувают файл БД. Компактизация удаляет их, после чего свободные страницы
+//! возвращаются VACUUM'ом (см. [`super::raw_storage::vacuum`]).
+
+use anyhow::Result;
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+use serde::Serialize;
+
+use super::db::get_connection;
+use super::projection_archive;
+
+/// Связь «значение колонки типа регистратора» → таблица агрегата-документа.
+struct RegistratorSource {
+    type_value: &'static str,
+    source_table: &'static str,
+}
+
+/// Проекция, которую умеет компактизировать задача.
+struct CompactionTarget {
+    table: &'static str,
+    /// Колонка с типом регистратора (в p900 это `document_type`).
+    type_column: &'static str,
+    sources: &'static [RegistratorSource],
+}
+
+const P904_SOURCES: &[RegistratorSource] = &[
+    RegistratorSource {
+        type_value: "WB_Sales",
+        source_table: "a012_wb_sales",
+    },
+    RegistratorSource {
+        type_value: "OZON_Transactions",
+        source_table: "a014_ozon_transactions",
+    },
+    RegistratorSource {
+        type_value: "YM_Order",
+        source_table: "a013_ym_order",
+    },
+    RegistratorSource {
+        type_value: "YM_Returns",
+        source_table: "a016_ym_returns",
+    },
+    RegistratorSource {
+        type_value: "OZON_FBS",
+        source_table: "a010_ozon_fbs_posting",
+    },
+    RegistratorSource {
+        type_value: "OZON_FBO",
+        source_table: "a011_ozon_fbo_posting",
+    },
+    RegistratorSource {
+        type_value: "OZON_Returns",
+        source_table: "a009_ozon_returns",
+    },
+];
+
+const P900_SOURCES: &[RegistratorSource] = &[
+    RegistratorSource {
+        type_value: "WB_Sales",
+        source_table: "a012_wb_sales",
+    },
+    RegistratorSource {
+        type_value: "YM_Order",
+        source_table: "a013_ym_order",
+    },
+    RegistratorSource {
+        type_value: "OZON_FBS_Posting",
+        source_table: "a010_ozon_fbs_posting",
+    },
+    RegistratorSource {
+        type_value: "OZON_FBO_Posting",
+        source_table: "a011_ozon_fbo_posting",
+    },
+    RegistratorSource {
+        type_value: "OZON_Returns",
+        source_table: "a009_ozon_returns",
+    },
+];
+
+const TARGETS: &[CompactionTarget] = &[
+    CompactionTarget {
+        table: "p900_sales_register",
+        type_column: "document_type",
+        sources: P900_SOURCES,
+    },
+    CompactionTarget {
+        table: "p904_sales_data",
+        type_column: "registrator_type",
+        sources: P904_SOURCES,
+    },
+    // Архивные месяцы (см. [`super::projection_archive`]) чистятся так же.
+    CompactionTarget {
+        table: "p900_sales_register_archive",
+        type_column: "document_type",
+        sources: P900_SOURCES,
+    },
+    CompactionTarget {
+        table: "p904_sales_data_archive",
+        type_column: "registrator_type",
+        sources: P904_SOURCES,
+    },
+];
+
+/// Таблицы, в которые пишет компактизация (для `TaskMetadata::write_tables`).
+pub const COMPACTION_TABLES: &[&str] = &[
+    "p900_sales_register",
+    "p904_sales_data",
+    "p900_sales_register_archive",
+    "p904_sales_data_archive",
+];
+
+/// Итог компактизации одной проекции.
+#[derive(Debug, Clone, Serialize)]
+pub struct TableCompactionReport {
+    pub table: String,
+    pub rows_before: i64,
+    /// Строки с непроведённым/удалённым/отсутствующим регистратором.
+    pub stale_rows: i64,
+    /// Фактически удалено (0 в режиме dry-run).
+    pub deleted_rows: u64,
+}
+
+/// Сводный отчёт по всем проекциям.
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct CompactionReport {
+    pub tables: Vec<TableCompactionReport>,
+    pub dry_run: bool,
+}
+
+impl CompactionReport {
+    pub fn total_stale(&self) -> i64 {
+        self.tables.iter().map(|t| t.stale_rows).sum()
+    }
+
+    pub fn total_deleted(&self) -> u64 {
+        self.tables.iter().map(|t| t.deleted_rows).sum()
+    }
+}
+
+/// Условие «регистратор строки не проведён» для одной связки тип → агрегат.
+/// Документ ищется и в архиве агрегата (см. [`super::projection_archive`]).
+fn stale_condition(target: &CompactionTarget, source: &RegistratorSource) -> String {
+    format!(
+        "{table}.{type_col} = '{type_value}' AND NOT EXISTS (\
+            SELECT 1 FROM {source} d \
+            WHERE d.id = {table}.registrator_ref AND d.is_posted = 1 AND d.is_deleted = 0\
+        )",
+        table = target.table,
+        type_col = target.type_column,
+        type_value = source.type_value,
+        source = projection_archive::source(source.source_table, None),
+    )
+}
+
+fn stale_where(target: &CompactionTarget) -> String {
+    target
+        .sources
+        .iter()
+        .map(|source| format!("({})", stale_condition(target, source)))
+        .collect::<Vec<_>>()
+        .join(" OR ")
+}
+
+async fn count(sql: String) -> Result<i64> {
+    let row = get_connection()
+        .query_one(Statement::from_string(DatabaseBackend::Sqlite, sql))
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("COUNT returned no row"))?;
+    Ok(row.try_get("", "cnt")?)
+}
+
+/// Удалить из проекций строки, регистратор которых не проведён, помечен на
+/// удаление или отсутствует. Типы регистраторов, не перечисленные в карте
+/// источников, не трогаются. В режиме `dry_run` только считает.
+pub async fn compact_projections(dry_run: bool) -> Result<CompactionReport> {
+    let mut report = CompactionReport {
+        tables: Vec::with_capacity(TARGETS.len()),
+        dry_run,
+    };
+
+    for target in TARGETS {
+        let condition = stale_where(target);
+        let rows_before = count(format!("SELECT COUNT(*) AS cnt FROM {}", target.table)).await?;
+        let stale_rows = count(format!(
+            "SELECT COUNT(*) AS cnt FROM {} WHERE {}",
+            target.table, condition
+        ))
+        .await?;
+
+        let deleted_rows = if dry_run || stale_rows == 0 {
+            0
+        } else {
+            get_connection()
+                .execute(Statement::from_string(
+                    DatabaseBackend::Sqlite,
+                    format!("DELETE FROM {} WHERE {}", target.table, condition),
+                ))
+                .await?
+                .rows_affected()
+        };
+
+        report.tables.push(TableCompactionReport {
+            table: target.table.to_string(),
+            rows_before,
+            stale_rows,
+            deleted_rows,
+        });
+    }
+
+    Ok(report)
+}
+
+/// Пересобрать индексы компактизированных проекций.
+pub async fn reindex_projections() -> Result<()> {
+    for table in COMPACTION_TABLES {
+        get_connection()
+            .execute(Statement::from_string(
+                DatabaseBackend::Sqlite,
+                format!("REINDEX {table}"),
+            ))
+            .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_condition_checks_posted_and_not_deleted_registrator() {
+        let target = &TARGETS[1];
+        let sql = stale_condition(target, &target.sources[0]);
+        assert!(sql.contains("p904_sales_data.registrator_type = 'WB_Sales'"));
+        assert!(sql.contains("a012_wb_sales"));
+        assert!(sql.contains("d.is_posted = 1 AND d.is_deleted = 0"));
+    }
+
+    #[test]
+    fn compaction_tables_match_targets() {
+        let tables: Vec<&str> = TARGETS.iter().map(|t| t.table).collect();
+        assert_eq!(tables, COMPACTION_TABLES);
+    }
+}
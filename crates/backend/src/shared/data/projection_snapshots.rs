@@ -0,0 +1,420 @@
+//! Снимки итогов проекции p900 для сравнения «как было на дату отчёта» и «как стало».
+//!
+//! Снимок фиксирует суммы p900 (рабочая таблица и архив) по месяцу, маркетплейсу и
+//! SKU за период на момент создания. Сравнение двух снимков или снимка с текущими
+//! данными отвечает на вопрос «почему выручка прошлого месяца изменилась после
+//! отчёта»: видно, какие SKU добавились, пропали или поменяли суммы (перепроведение,
+//! поздние возвраты, переназначение документов).
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+use chrono::{Months, NaiveDate, Utc};
+use contracts::system::projection_snapshots::{
+    CreateProjectionSnapshotRequest, ProjectionSnapshotDto, SnapshotCompareResponse,
+    SnapshotDiffRowDto, SnapshotDiffStatus, SnapshotPeriodTotalDto, SNAPSHOT_CURRENT,
+};
+use sea_orm::{ConnectionTrait, DatabaseBackend, QueryResult, Statement, TransactionTrait, Value};
+use uuid::Uuid;
+
+use super::db::get_connection;
+use super::projection_archive;
+
+/// Строк сравнения в одном ответе.
+const COMPARE_MAX_ROWS: usize = 5000;
+/// Расхождение меньше копейки считается округлением.
+const EPSILON: f64 = 0.005;
+const MAX_LABEL_LEN: usize = 200;
+
+/// Итоги одной строки снимка: (месяц, маркетплейс, SKU) → суммы.
+type TotalsKey = (String, String, String);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Totals {
+    qty: f64,
+    revenue: f64,
+    cost: f64,
+}
+
+/// Запрос итогов p900 за `[date_from, date_to)`; параметры — границы дат.
+fn totals_select(date_from: &str) -> String {
+    format!(
+        "SELECT substr(sale_date, 1, 7) AS period, marketplace, \
+                COALESCE(NULLIF(seller_sku, ''), mp_item_id) AS sku, \
+                SUM(qty) AS qty, \
+                SUM(COALESCE(amount_line, 0)) AS revenue, \
+                SUM(COALESCE(cost, 0)) AS cost \
+         FROM {} \
+         WHERE sale_date >= ? AND sale_date < ? \
+         GROUP BY 1, 2, 3",
+        projection_archive::source("p900_sales_register", Some(date_from))
+    )
+}
+
+/// Границы дат периода: первый день `from` и первый день месяца после `to`.
+fn period_bounds(from: &str, to: &str) -> Result<(String, String)> {
+    projection_archive::validate_month(from)?;
+    projection_archive::validate_month(to)?;
+    if from > to {
+        bail!("Invalid period: {from} позже {to}");
+    }
+    let start = NaiveDate::parse_from_str(&format!("{from}-01"), "%Y-%m-%d")?;
+    let end = NaiveDate::parse_from_str(&format!("{to}-01"), "%Y-%m-%d")?
+        .checked_add_months(Months::new(1))
+        .ok_or_else(|| anyhow::anyhow!("Invalid period end {to}"))?;
+    Ok((
+        start.format("%Y-%m-%d").to_string(),
+        end.format("%Y-%m-%d").to_string(),
+    ))
+}
+
+fn to_dto(row: &QueryResult) -> Result<ProjectionSnapshotDto> {
+    Ok(ProjectionSnapshotDto {
+        id: row.try_get("", "id")?,
+        label: row.try_get("", "label")?,
+        period_from: row.try_get("", "period_from")?,
+        period_to: row.try_get("", "period_to")?,
+        row_count: row.try_get("", "row_count")?,
+        revenue: row.try_get("", "revenue")?,
+        created_by: row.try_get("", "created_by")?,
+        created_at: row.try_get("", "created_at")?,
+    })
+}
+
+pub async fn list() -> Result<Vec<ProjectionSnapshotDto>> {
+    let rows = get_connection()
+        .query_all(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT * FROM sys_projection_snapshots ORDER BY created_at DESC".to_string(),
+        ))
+        .await?;
+    rows.iter().map(to_dto).collect()
+}
+
+pub async fn get(id: &str) -> Result<Option<ProjectionSnapshotDto>> {
+    let row = get_connection()
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT * FROM sys_projection_snapshots WHERE id = ?",
+            vec![id.into()],
+        ))
+        .await?;
+    row.as_ref().map(to_dto).transpose()
+}
+
+/// Фиксирует итоги p900 за период одной транзакцией.
+pub async fn create(
+    req: &CreateProjectionSnapshotRequest,
+    username: &str,
+) -> Result<ProjectionSnapshotDto> {
+    let label = req.label.trim();
+    if label.is_empty() || label.chars().count() > MAX_LABEL_LEN {
+        bail!("Invalid label: от 1 до {MAX_LABEL_LEN} символов");
+    }
+    let (date_from, date_to) = period_bounds(&req.period_from, &req.period_to)?;
+    let id = Uuid::new_v4().to_string();
+
+    let txn = get_connection().begin().await?;
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        &format!(
+            "INSERT INTO sys_projection_snapshot_rows \
+                 (snapshot_id, period, marketplace, sku, qty, revenue, cost) \
+             SELECT ?, period, marketplace, sku, qty, revenue, cost FROM ({})",
+            totals_select(&date_from)
+        ),
+        vec![id.clone().into(), date_from.into(), date_to.into()],
+    ))
+    .await?;
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO sys_projection_snapshots \
+             (id, label, period_from, period_to, row_count, revenue, created_by, created_at) \
+         SELECT ?, ?, ?, ?, COUNT(*), COALESCE(SUM(revenue), 0), ?, ? \
+         FROM sys_projection_snapshot_rows WHERE snapshot_id = ?",
+        vec![
+            id.clone().into(),
+            label.into(),
+            req.period_from.clone().into(),
+            req.period_to.clone().into(),
+            username.into(),
+            Utc::now().to_rfc3339().into(),
+            id.clone().into(),
+        ],
+    ))
+    .await?;
+    txn.commit().await?;
+
+    let snapshot = get(&id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Snapshot {id} disappeared after insert"))?;
+    tracing::info!(
+        "Projection snapshot '{}' {}..{} by {}: {} rows",
+        snapshot.label,
+        snapshot.period_from,
+        snapshot.period_to,
+        username,
+        snapshot.row_count
+    );
+    Ok(snapshot)
+}
+
+/// Удаляет снимок; `false` — снимка нет.
+pub async fn delete(id: &str) -> Result<bool> {
+    let txn = get_connection().begin().await?;
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM sys_projection_snapshot_rows WHERE snapshot_id = ?",
+        vec![id.into()],
+    ))
+    .await?;
+    let deleted = txn
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM sys_projection_snapshots WHERE id = ?",
+            vec![id.into()],
+        ))
+        .await?
+        .rows_affected();
+    txn.commit().await?;
+    Ok(deleted > 0)
+}
+
+async fn load_totals(sql: &str, values: Vec<Value>) -> Result<BTreeMap<TotalsKey, Totals>> {
+    let rows = get_connection()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            sql,
+            values,
+        ))
+        .await?;
+    let mut totals = BTreeMap::new();
+    for row in rows {
+        totals.insert(
+            (
+                row.try_get("", "period")?,
+                row.try_get("", "marketplace")?,
+                row.try_get::<Option<String>>("", "sku")?
+                    .unwrap_or_default(),
+            ),
+            Totals {
+                qty: row.try_get::<Option<f64>>("", "qty")?.unwrap_or(0.0),
+                revenue: row.try_get::<Option<f64>>("", "revenue")?.unwrap_or(0.0),
+                cost: row.try_get::<Option<f64>>("", "cost")?.unwrap_or(0.0),
+            },
+        );
+    }
+    Ok(totals)
+}
+
+async fn snapshot_totals(id: &str, from: &str, to: &str) -> Result<BTreeMap<TotalsKey, Totals>> {
+    load_totals(
+        "SELECT period, marketplace, sku, qty, revenue, cost \
+         FROM sys_projection_snapshot_rows \
+         WHERE snapshot_id = ? AND period >= ? AND period <= ?",
+        vec![id.into(), from.into(), to.into()],
+    )
+    .await
+}
+
+fn differs(a: f64, b: f64) -> bool {
+    (a - b).abs() >= EPSILON
+}
+
+/// Построчное сравнение итогов; строки отсутствующие в одном из снимков — `Added` / `Removed`.
+fn diff_totals(
+    base: &BTreeMap<TotalsKey, Totals>,
+    target: &BTreeMap<TotalsKey, Totals>,
+) -> Vec<SnapshotDiffRowDto> {
+    let mut keys: Vec<&TotalsKey> = base.keys().chain(target.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .map(|key| {
+            let (b, t) = (base.get(key), target.get(key));
+            let status = match (b, t) {
+                (None, Some(_)) => SnapshotDiffStatus::Added,
+                (Some(_), None) => SnapshotDiffStatus::Removed,
+                (Some(b), Some(t))
+                    if differs(b.qty, t.qty)
+                        || differs(b.revenue, t.revenue)
+                        || differs(b.cost, t.cost) =>
+                {
+                    SnapshotDiffStatus::Changed
+                }
+                _ => SnapshotDiffStatus::Same,
+            };
+            let (b, t) = (
+                b.copied().unwrap_or_default(),
+                t.copied().unwrap_or_default(),
+            );
+            SnapshotDiffRowDto {
+                period: key.0.clone(),
+                marketplace: key.1.clone(),
+                sku: key.2.clone(),
+                base_qty: b.qty,
+                target_qty: t.qty,
+                base_revenue: b.revenue,
+                target_revenue: t.revenue,
+                base_cost: b.cost,
+                target_cost: t.cost,
+                status,
+            }
+        })
+        .collect()
+}
+
+/// Итоги по месяцам (все строки, до фильтра «только изменения»).
+fn period_totals(rows: &[SnapshotDiffRowDto]) -> Vec<SnapshotPeriodTotalDto> {
+    let mut periods: BTreeMap<&str, SnapshotPeriodTotalDto> = BTreeMap::new();
+    for row in rows {
+        let total = periods
+            .entry(row.period.as_str())
+            .or_insert_with(|| SnapshotPeriodTotalDto {
+                period: row.period.clone(),
+                base_revenue: 0.0,
+                target_revenue: 0.0,
+                changed_rows: 0,
+            });
+        total.base_revenue += row.base_revenue;
+        total.target_revenue += row.target_revenue;
+        if row.status != SnapshotDiffStatus::Same {
+            total.changed_rows += 1;
+        }
+    }
+    periods.into_values().collect()
+}
+
+/// Сравнивает снимок `base` со снимком `target` или с текущими данными
+/// ([`SNAPSHOT_CURRENT`]) за пересечение их периодов. `None` — снимок не найден.
+pub async fn compare(
+    base_id: &str,
+    target_id: &str,
+    only_changed: bool,
+) -> Result<Option<SnapshotCompareResponse>> {
+    let Some(base) = get(base_id).await? else {
+        return Ok(None);
+    };
+    let target = if target_id == SNAPSHOT_CURRENT {
+        None
+    } else {
+        match get(target_id).await? {
+            Some(snapshot) => Some(snapshot),
+            None => return Ok(None),
+        }
+    };
+
+    let (period_from, period_to) = match &target {
+        Some(t) => (
+            base.period_from.clone().max(t.period_from.clone()),
+            base.period_to.clone().min(t.period_to.clone()),
+        ),
+        None => (base.period_from.clone(), base.period_to.clone()),
+    };
+    if period_from > period_to {
+        bail!(
+            "Invalid comparison: периоды снимков не пересекаются ({}..{} и {}..{})",
+            base.period_from,
+            base.period_to,
+            target
+                .as_ref()
+                .map(|t| t.period_from.as_str())
+                .unwrap_or(""),
+            target.as_ref().map(|t| t.period_to.as_str()).unwrap_or("")
+        );
+    }
+
+    let base_totals = snapshot_totals(&base.id, &period_from, &period_to).await?;
+    let target_totals = match &target {
+        Some(t) => snapshot_totals(&t.id, &period_from, &period_to).await?,
+        None => {
+            let (date_from, date_to) = period_bounds(&period_from, &period_to)?;
+            load_totals(
+                &totals_select(&date_from),
+                vec![date_from.into(), date_to.into()],
+            )
+            .await?
+        }
+    };
+
+    let mut rows = diff_totals(&base_totals, &target_totals);
+    let periods = period_totals(&rows);
+    let changed_rows = periods.iter().map(|p| p.changed_rows).sum();
+    if only_changed {
+        rows.retain(|r| r.status != SnapshotDiffStatus::Same);
+    }
+    rows.sort_by(|a, b| {
+        let delta = |r: &SnapshotDiffRowDto| (r.target_revenue - r.base_revenue).abs();
+        delta(b).total_cmp(&delta(a))
+    });
+    let truncated = rows.len() > COMPARE_MAX_ROWS;
+    rows.truncate(COMPARE_MAX_ROWS);
+
+    Ok(Some(SnapshotCompareResponse {
+        base_label: base.label,
+        target_label: target
+            .map(|t| t.label)
+            .unwrap_or_else(|| "Текущие данные".to_string()),
+        period_from,
+        period_to,
+        periods,
+        rows,
+        changed_rows,
+        truncated,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(period: &str, sku: &str) -> TotalsKey {
+        (period.to_string(), "WB".to_string(), sku.to_string())
+    }
+
+    fn totals(qty: f64, revenue: f64) -> Totals {
+        Totals {
+            qty,
+            revenue,
+            cost: 0.0,
+        }
+    }
+
+    #[test]
+    fn period_bounds_cover_whole_months() {
+        assert_eq!(
+            period_bounds("2025-11", "2025-12").unwrap(),
+            ("2025-11-01".to_string(), "2026-01-01".to_string())
+        );
+        assert!(period_bounds("2025-12", "2025-11").is_err());
+        assert!(period_bounds("2025-13", "2025-12").is_err());
+    }
+
+    #[test]
+    fn diff_marks_added_removed_changed_and_same() {
+        let base = BTreeMap::from([
+            (key("2025-09", "A"), totals(1.0, 100.0)),
+            (key("2025-09", "B"), totals(2.0, 200.0)),
+            (key("2025-09", "C"), totals(3.0, 300.0)),
+        ]);
+        let target = BTreeMap::from([
+            (key("2025-09", "A"), totals(1.0, 100.001)),
+            (key("2025-09", "B"), totals(1.0, 100.0)),
+            (key("2025-09", "D"), totals(5.0, 500.0)),
+        ]);
+        let rows = diff_totals(&base, &target);
+        let status = |sku: &str| rows.iter().find(|r| r.sku == sku).unwrap().status;
+
+        assert_eq!(rows.len(), 4);
+        assert_eq!(status("A"), SnapshotDiffStatus::Same);
+        assert_eq!(status("B"), SnapshotDiffStatus::Changed);
+        assert_eq!(status("C"), SnapshotDiffStatus::Removed);
+        assert_eq!(status("D"), SnapshotDiffStatus::Added);
+
+        let periods = period_totals(&rows);
+        assert_eq!(periods.len(), 1);
+        assert_eq!(periods[0].changed_rows, 3);
+        assert!((periods[0].base_revenue - 600.0).abs() < 1e-9);
+        assert!((periods[0].target_revenue - 700.001).abs() < 1e-9);
+    }
+}
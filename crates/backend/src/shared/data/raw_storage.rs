@@ -1,8 +1,9 @@
 use anyhow::Result;
 use chrono::Utc;
 use contracts::system::raw_storage::{
-    DbVacuumResult, DbVacuumStatus, DbWalCheckpointResult, RawStorageCleanupMode,
-    RawStorageCleanupPreview, RawStorageCleanupRequest, RawStorageStatus, RawStorageTypeStat,
+    DbVacuumResult, DbVacuumStatus, DbWalCheckpointResult, RawPayloadVersionDto,
+    RawStorageCleanupMode, RawStorageCleanupPreview, RawStorageCleanupRequest, RawStorageStatus,
+    RawStorageTypeStat, RAW_VERSIONS_KEPT,
 };
 use sea_orm::entity::prelude::*;
 use sea_orm::{
@@ -11,6 +12,8 @@ use sea_orm::{
 };
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::cell::Cell;
+use std::future::Future;
 use std::path::PathBuf;
 use uuid::Uuid;
 
@@ -53,6 +56,42 @@ fn raw_hash(raw_json: &str) -> String {
     format!("{:x}", Sha256::digest(raw_json.as_bytes()))
 }
 
+/// Счётчики [`save_raw_json`] за один импорт.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RawDedupStats {
+    /// Записано новых raw JSON
+    pub stored: u64,
+    /// Повторная выгрузка с тем же sha256: документ связан с уже сохранённой записью
+    pub deduplicated: u64,
+}
+
+tokio::task_local! {
+    static DEDUP_STATS: Cell<RawDedupStats>;
+}
+
+fn record_dedup(deduplicated: bool) {
+    let _ = DEDUP_STATS.try_with(|cell| {
+        let mut stats = cell.get();
+        if deduplicated {
+            stats.deduplicated += 1;
+        } else {
+            stats.stored += 1;
+        }
+        cell.set(stats);
+    });
+}
+
+/// Выполняет `fut`, подсчитывая сохранённые и дедуплицированные raw JSON.
+/// Вызовы `save_raw_json` из задач, запущенных внутри через `tokio::spawn`, не учитываются.
+pub async fn with_dedup_stats<F: Future>(fut: F) -> (F::Output, RawDedupStats) {
+    DEDUP_STATS
+        .scope(Cell::new(RawDedupStats::default()), async {
+            let output = fut.await;
+            (output, DEDUP_STATS.with(Cell::get))
+        })
+        .await
+}
+
 /// Сохранить сырой JSON ответ от API маркетплейса
 /// Возвращает уникальный ref (id записи) для использования в source_ref
 pub async fn save_raw_json(
@@ -76,6 +115,7 @@ pub async fn save_raw_json(
         .one(conn())
         .await?
     {
+        record_dedup(true);
         return Ok(Some(existing.id));
     }
 
@@ -93,6 +133,7 @@ pub async fn save_raw_json(
     };
 
     active.insert(conn()).await?;
+    record_dedup(false);
 
     tracing::debug!(
         "Saved raw JSON: marketplace={}, document_type={}, document_no={}, id={}",
@@ -116,6 +157,17 @@ pub async fn get_by_ref(ref_id: &str) -> Result<Option<String>> {
     Ok(result.map(|m| m.raw_json))
 }
 
+/// Номер документа, к которому относится сохранённый raw JSON.
+pub async fn document_no_by_ref(ref_id: &str) -> Result<Option<String>> {
+    if ref_id.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let result = Entity::find_by_id(ref_id.to_string()).one(conn()).await?;
+
+    Ok(result.map(|m| m.document_no))
+}
+
 pub async fn get_json_value_by_ref(ref_id: &str) -> Result<serde_json::Value> {
     match get_by_ref(ref_id).await? {
         Some(raw_json) => serde_json::from_str(&raw_json).map_err(Into::into),
@@ -143,11 +195,51 @@ pub async fn get_by_key(
     Ok(result)
 }
 
+/// Все сохранённые версии raw JSON документа, на одну из которых указывает `ref_id`.
+/// Версии нумеруются от самой ранней; текущей считается версия с `id == ref_id`.
+pub async fn list_versions_by_ref(ref_id: &str) -> Result<Vec<RawPayloadVersionDto>> {
+    if ref_id.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rows = conn()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT v.id, v.fetched_at, v.created_at, v.raw_hash, length(v.raw_json) AS bytes
+             FROM document_raw_storage v
+             JOIN document_raw_storage cur
+               ON cur.marketplace = v.marketplace
+              AND cur.document_type = v.document_type
+              AND cur.document_no = v.document_no
+             WHERE cur.id = ?
+             ORDER BY v.created_at, v.id",
+            [ref_id.into()],
+        ))
+        .await?;
+
+    let mut versions = Vec::with_capacity(rows.len());
+    for (idx, row) in rows.into_iter().enumerate() {
+        let id: String = row.try_get("", "id")?;
+        let bytes: i64 = row.try_get("", "bytes")?;
+        versions.push(RawPayloadVersionDto {
+            is_current: id == ref_id,
+            id,
+            version: idx as u32 + 1,
+            fetched_at: row.try_get("", "fetched_at")?,
+            created_at: row.try_get("", "created_at")?,
+            raw_hash: row.try_get("", "raw_hash")?,
+            size_bytes: bytes.max(0) as u64,
+        });
+    }
+    Ok(versions)
+}
+
 const REFERENCED_REFS_CTE: &str = r#"
 WITH refs(ref) AS (
     SELECT json_extract(source_meta_json, '$.raw_payload_ref') FROM a010_ozon_fbs_posting
     UNION ALL SELECT json_extract(source_meta_json, '$.raw_payload_ref') FROM a011_ozon_fbo_posting
     UNION ALL SELECT json_extract(source_meta_json, '$.raw_payload_ref') FROM a012_wb_sales
+    UNION ALL SELECT json_extract(source_meta_json, '$.raw_payload_ref') FROM a012_wb_sales_archive
     UNION ALL SELECT json_extract(source_meta_json, '$.raw_payload_ref') FROM a013_ym_order
     UNION ALL SELECT json_extract(source_meta_json, '$.raw_payload_ref') FROM a015_wb_orders
     UNION ALL SELECT json_extract(source_meta_json, '$.marketplace_raw_payload_ref') FROM a015_wb_orders
@@ -160,11 +252,42 @@ clean_refs(ref) AS (
 )
 "#;
 
+/// [`REFERENCED_REFS_CTE`] + `kept_versions`: последние [`RAW_VERSIONS_KEPT`]
+/// версий документов, на которые ещё есть ссылка. Очистка их не трогает,
+/// чтобы во вкладке Raw JSON оставалась история повторных загрузок.
+fn cleanup_cte() -> String {
+    format!(
+        r#"{REFERENCED_REFS_CTE},
+kept_versions(id) AS (
+    SELECT id FROM (
+        SELECT
+            s.id,
+            row_number() OVER (
+                PARTITION BY s.marketplace, s.document_type, s.document_no
+                ORDER BY s.created_at DESC, s.id DESC
+            ) AS rn
+        FROM document_raw_storage s
+        WHERE EXISTS (
+            SELECT 1 FROM document_raw_storage r
+            WHERE r.marketplace = s.marketplace
+              AND r.document_type = s.document_type
+              AND r.document_no = s.document_no
+              AND r.id IN (SELECT ref FROM clean_refs)
+        )
+    )
+    WHERE rn <= {keep}
+)
+"#,
+        keep = RAW_VERSIONS_KEPT + 1
+    )
+}
+
 fn cleanup_where(req: &RawStorageCleanupRequest) -> Result<String> {
     match req.mode {
-        RawStorageCleanupMode::Unreferenced => {
-            Ok("id NOT IN (SELECT ref FROM clean_refs)".to_string())
-        }
+        RawStorageCleanupMode::Unreferenced => Ok(
+            "id NOT IN (SELECT ref FROM clean_refs) AND id NOT IN (SELECT id FROM kept_versions)"
+                .to_string(),
+        ),
         RawStorageCleanupMode::All => Ok("1 = 1".to_string()),
         RawStorageCleanupMode::OlderThanDays => {
             let days = req
@@ -175,7 +298,7 @@ fn cleanup_where(req: &RawStorageCleanupRequest) -> Result<String> {
             }
             let cutoff = (Utc::now() - chrono::Duration::days(days)).to_rfc3339();
             Ok(format!(
-                "created_at < '{}' AND id NOT IN (SELECT ref FROM clean_refs)",
+                "created_at < '{}' AND id NOT IN (SELECT ref FROM clean_refs) AND id NOT IN (SELECT id FROM kept_versions)",
                 cutoff.replace('\'', "''")
             ))
         }
@@ -228,6 +351,17 @@ pub async fn status() -> Result<RawStorageStatus> {
         .ok_or_else(|| anyhow::anyhow!("raw storage referenced query returned no row"))?;
     let referenced_rows: i64 = referenced_row.try_get("", "rows")?;
 
+    let history_sql = format!(
+        "{} SELECT COUNT(*) AS rows FROM document_raw_storage
+         WHERE id IN (SELECT id FROM kept_versions) AND id NOT IN (SELECT ref FROM clean_refs)",
+        cleanup_cte()
+    );
+    let history_row = conn
+        .query_one(Statement::from_string(DatabaseBackend::Sqlite, history_sql))
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("raw storage history query returned no row"))?;
+    let history_rows: i64 = history_row.try_get("", "rows")?;
+
     let by_type_rows = conn
         .query_all(Statement::from_string(
             DatabaseBackend::Sqlite,
@@ -257,6 +391,7 @@ pub async fn status() -> Result<RawStorageStatus> {
         total_mb: total_bytes as f64 / 1024.0 / 1024.0,
         referenced_rows: referenced_rows.max(0) as u64,
         unreferenced_rows: total_rows.saturating_sub(referenced_rows).max(0) as u64,
+        history_rows: history_rows.max(0) as u64,
         by_type,
     })
 }
@@ -267,7 +402,8 @@ pub async fn cleanup_preview(req: &RawStorageCleanupRequest) -> Result<RawStorag
         "{} SELECT COUNT(*) AS rows, COALESCE(SUM(length(raw_json)), 0) AS bytes
          FROM document_raw_storage
          WHERE {}",
-        REFERENCED_REFS_CTE, where_sql
+        cleanup_cte(),
+        where_sql
     );
     let row = conn()
         .query_one(Statement::from_string(DatabaseBackend::Sqlite, sql))
@@ -290,7 +426,8 @@ pub async fn cleanup(req: &RawStorageCleanupRequest) -> Result<RawStorageCleanup
     let where_sql = cleanup_where(req)?;
     let sql = format!(
         "{} DELETE FROM document_raw_storage WHERE {}",
-        REFERENCED_REFS_CTE, where_sql
+        cleanup_cte(),
+        where_sql
     );
     conn()
         .execute(Statement::from_string(DatabaseBackend::Sqlite, sql))
@@ -411,3 +548,30 @@ pub async fn vacuum() -> Result<DbVacuumResult> {
         wal_truncated: wal.truncated,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn dedup_stats_are_scoped_to_the_future() {
+        let ((), stats) = with_dedup_stats(async {
+            record_dedup(false);
+            record_dedup(true);
+            record_dedup(true);
+        })
+        .await;
+        assert_eq!(
+            stats,
+            RawDedupStats {
+                stored: 1,
+                deduplicated: 2
+            }
+        );
+
+        // Вне области учёта вызов ничего не делает
+        record_dedup(true);
+        let ((), empty) = with_dedup_stats(async {}).await;
+        assert_eq!(empty, RawDedupStats::default());
+    }
+}
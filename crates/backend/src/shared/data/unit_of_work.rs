@@ -0,0 +1,80 @@
+//! Unit of work: все записи одной команды (документ, проекции, GL, лента CDC)
+//! в одной транзакции БД.
+//!
+//! Проведение раньше писало документ и проекции последовательными autocommit-вызовами,
+//! и ошибка посередине оставляла частичное состояние: документ проведён, а проекций
+//! нет (или удалены старые, а новые не записаны). Через `UnitOfWork` все записи идут
+//! в `uow.conn()`, фиксация одна, при ошибке откатывается всё.
+//!
+//! Чтения и сборку строк делайте до [`UnitOfWork::begin`] — транзакция держит
+//! write-lock SQLite, пока открыта. Побочные эффекты вне БД (сигналы change token и
+//! т.п.) регистрируются через [`UnitOfWork::after_commit`] и выполняются только после
+//! успешной фиксации.
+//!
+//! ```rust
+//! let mut uow = UnitOfWork::begin().await?;
+//! repository::update_posted_document_with_conn(uow.conn(), &document).await?;
+//! p909::service::remove_by_registrator_ref_with_conn(uow.conn(), &p909_ref).await?;
+//! uow.record_changes(&[repository::change_event(id, ChangeOp::Posted)]).await?;
+//! uow.after_commit(|| super::change_token::TOKEN.bump());
+//! uow.commit().await?;
+//! ```
+//!
+//! Если `commit` не вызван (ранний `?`), транзакция откатывается при drop.
+
+use anyhow::Result;
+use contracts::system::document_audit::DocumentAuditAction;
+use sea_orm::{DatabaseTransaction, TransactionTrait};
+
+use super::db::get_connection;
+use crate::system::cdc::service::ChangeEvent;
+
+type AfterCommitHook = Box<dyn FnOnce() + Send + Sync>;
+
+pub struct UnitOfWork {
+    txn: DatabaseTransaction,
+    after_commit: Vec<AfterCommitHook>,
+}
+
+impl UnitOfWork {
+    pub async fn begin() -> Result<Self> {
+        Ok(Self {
+            txn: get_connection().begin().await?,
+            after_commit: Vec::new(),
+        })
+    }
+
+    /// Соединение для `*_with_conn` функций репозиториев.
+    pub fn conn(&self) -> &DatabaseTransaction {
+        &self.txn
+    }
+
+    /// События ленты CDC пишутся в той же транзакции, что и данные.
+    pub async fn record_changes(&self, events: &[ChangeEvent]) -> Result<()> {
+        crate::system::cdc::service::record_with_conn(&self.txn, events).await
+    }
+
+    /// Проведение или отмена проведения в журнал «История» — в той же транзакции.
+    pub async fn record_audit(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+        action: DocumentAuditAction,
+    ) -> Result<()> {
+        crate::system::document_audit::record_with_conn(&self.txn, entity_type, entity_id, action)
+            .await
+    }
+
+    /// Действие после успешной фиксации; при откате не выполняется.
+    pub fn after_commit(&mut self, hook: impl FnOnce() + Send + Sync + 'static) {
+        self.after_commit.push(Box::new(hook));
+    }
+
+    pub async fn commit(self) -> Result<()> {
+        self.txn.commit().await?;
+        for hook in self.after_commit {
+            hook();
+        }
+        Ok(())
+    }
+}
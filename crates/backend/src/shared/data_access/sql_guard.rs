@@ -7,6 +7,8 @@ use sqlparser::parser::Parser;
 use std::collections::HashSet;
 use std::ops::ControlFlow;
 
+use crate::shared::data::projection_archive;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ReadQueryInfo {
     pub tables: Vec<String>,
@@ -199,9 +201,17 @@ pub fn inspect_read_query(sql: &str) -> Result<ReadQueryInfo, String> {
     Ok(ReadQueryInfo { tables })
 }
 
+/// Wraps an already inspected SELECT into a row limit. Archived tables it reads
+/// (see `projection_archive`) are widened to include their archive rows.
 pub fn wrap_limited_sql(sql: &str, row_limit: usize, alias: &str) -> String {
     let statement = sql.trim().trim_end_matches(';').trim();
-    format!("SELECT * FROM ({statement}) AS {alias} LIMIT {row_limit}")
+    let tables = inspect_read_query(statement)
+        .map(|info| info.tables)
+        .unwrap_or_default();
+    projection_archive::with_archive(
+        format!("SELECT * FROM ({statement}) AS {alias} LIMIT {row_limit}"),
+        &tables,
+    )
 }
 
 #[cfg(test)]
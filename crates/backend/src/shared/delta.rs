@@ -0,0 +1,75 @@
+//! Дельта-режим list-эндпоинтов (`contracts::shared::delta`).
+//!
+//! Момент прошлой синхронизации приходит параметром `updated_after` (RFC 3339)
+//! или заголовком `If-Modified-Since` (HTTP-date). Метка `server_time` для
+//! следующего запроса берётся ДО выборки: изменение, записанное между выборкой
+//! и ответом, придёт повторно в следующей дельте, но не потеряется.
+
+use axum::http::{header, HeaderMap};
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// Текущее время сервера — водяной знак для следующего `updated_after`.
+pub fn server_time() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Момент, начиная с которого нужны изменения. `None` — обычный (полный) запрос.
+pub fn parse_since(
+    updated_after: Option<&str>,
+    headers: &HeaderMap,
+) -> Result<Option<DateTime<Utc>>, String> {
+    if let Some(raw) = updated_after.map(str::trim).filter(|s| !s.is_empty()) {
+        return DateTime::parse_from_rfc3339(raw)
+            .map(|dt| Some(dt.with_timezone(&Utc)))
+            .map_err(|_| format!("updated_after: ожидается RFC 3339, получено «{}»", raw));
+    }
+    let Some(raw) = headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return Ok(None);
+    };
+    DateTime::parse_from_rfc2822(raw.trim())
+        .map(|dt| Some(dt.with_timezone(&Utc)))
+        .map_err(|_| format!("If-Modified-Since: некорректная дата «{}»", raw))
+}
+
+/// Условие «колонка изменена не раньше `since`» (включительно: повтор лучше пропуска).
+///
+/// `julianday` сравнивает моменты, а не строки, поэтому формат хранения
+/// (`T`/пробел, смещение, число знаков после запятой) не важен.
+pub fn changed_since_sql(column: &str, since: &DateTime<Utc>) -> String {
+    format!(
+        "julianday({}) >= julianday('{}')",
+        column,
+        since.to_rfc3339_opts(SecondsFormat::Millis, true)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn parses_param_then_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_since(None, &headers), Ok(None));
+
+        headers.insert(
+            header::IF_MODIFIED_SINCE,
+            HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT"),
+        );
+        let from_header = parse_since(None, &headers).unwrap().unwrap();
+        assert_eq!(from_header.to_rfc3339(), "1994-11-06T08:49:37+00:00");
+
+        let from_param = parse_since(Some("2026-03-01T10:00:00.250+03:00"), &headers)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            changed_since_sql("s.updated_at", &from_param),
+            "julianday(s.updated_at) >= julianday('2026-03-01T07:00:00.250Z')"
+        );
+        assert!(parse_since(Some("вчера"), &headers).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+//! Единый тип ошибки HTTP-обработчиков.
+//!
+//! Обработчик возвращает `Result<_, AppError>`; ответ — статус и JSON-конверт
+//! [`ApiErrorBody`] (code, message, details, correlation_id), который фронтенд
+//! показывает пользователю вместо «Server error: 500».
+//!
+//! Любая `anyhow`-совместимая ошибка превращается во [`AppError::Internal`] через `?`,
+//! поэтому контекст добавляется обычным `anyhow::Context`:
+//!
+//! ```rust
+//! let items = repository::list().await.context("Failed to list sales")?;
+//! ```
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use contracts::shared::api_error::ApiErrorBody;
+use serde::Serialize;
+
+use crate::system::middleware::request_logger::current_request_id;
+
+#[derive(Debug)]
+pub enum AppError {
+    /// 400: некорректные параметры запроса
+    BadRequest(String),
+    /// 403: недостаточно прав на операцию
+    Forbidden(String),
+    /// 404: сущность не найдена
+    NotFound(String),
+    /// 409: состояние изменилось (например, версия документа)
+    Conflict {
+        message: String,
+        details: Option<serde_json::Value>,
+    },
+    /// 500: причина пишется в журнал и в `details`
+    Internal(anyhow::Error),
+}
+
+impl AppError {
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::BadRequest(message.into())
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::Forbidden(message.into())
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound(message.into())
+    }
+
+    pub fn conflict(message: impl Into<String>, details: impl Serialize) -> Self {
+        Self::Conflict {
+            message: message.into(),
+            details: serde_json::to_value(details).ok(),
+        }
+    }
+
+    /// Некорректный UUID в пути запроса
+    pub fn invalid_id(id: &str) -> Self {
+        Self::BadRequest(format!("Некорректный идентификатор: {}", id))
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict { .. } => StatusCode::CONFLICT,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn code(&self) -> &'static str {
+        match self {
+            Self::BadRequest(_) => "bad_request",
+            Self::Forbidden(_) => "forbidden",
+            Self::NotFound(_) => "not_found",
+            Self::Conflict { .. } => "conflict",
+            Self::Internal(_) => "internal",
+        }
+    }
+}
+
+/// `?` над `anyhow::Result`, `DbErr`, `serde_json::Error` и т.п. даёт 500.
+impl<E> From<E> for AppError
+where
+    E: Into<anyhow::Error>,
+{
+    fn from(err: E) -> Self {
+        Self::Internal(err.into())
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let code = self.code();
+        let (message, details) = match self {
+            Self::BadRequest(message) | Self::Forbidden(message) | Self::NotFound(message) => {
+                tracing::warn!("{} {}: {}", status.as_u16(), code, message);
+                (message, None)
+            }
+            Self::Conflict { message, details } => {
+                tracing::warn!("{} {}: {}", status.as_u16(), code, message);
+                (message, details)
+            }
+            Self::Internal(err) => {
+                tracing::error!("{:#}", err);
+                (
+                    "Внутренняя ошибка сервера".to_string(),
+                    Some(serde_json::Value::String(format!("{:#}", err))),
+                )
+            }
+        };
+
+        let body = ApiErrorBody {
+            code: code.to_string(),
+            message,
+            details,
+            correlation_id: current_request_id(),
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anyhow_errors_become_internal_with_context() {
+        let result: anyhow::Result<()> = Err(anyhow::anyhow!("db locked"));
+        let err: AppError = anyhow::Context::context(result, "Failed to post")
+            .unwrap_err()
+            .into();
+        assert_eq!(err.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.code(), "internal");
+        match err {
+            AppError::Internal(e) => assert_eq!(format!("{:#}", e), "Failed to post: db locked"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn conflict_carries_serialized_details() {
+        let err = AppError::conflict("stale", serde_json::json!({"current_version": 3}));
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+        match err {
+            AppError::Conflict { details, .. } => {
+                assert_eq!(details, Some(serde_json::json!({"current_version": 3})))
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
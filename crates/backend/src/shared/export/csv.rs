@@ -0,0 +1,198 @@
+//! Потоковая CSV-выгрузка списков для Excel: разделитель `;`, UTF-8 BOM,
+//! десятичная запятая. Строки читаются из БД страницами и уходят клиенту по
+//! мере чтения — весь датасет в памяти не собирается.
+//!
+//! Ошибка чтения посреди выгрузки обрывает поток: клиент получит
+//! недокачанный файл, а не «успешный» CSV без хвоста.
+
+use std::future::Future;
+use std::io;
+
+use axum::body::{Body, Bytes};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use futures_util::stream::{self, Stream, StreamExt};
+use serde::Deserialize;
+
+/// Строк в одной странице чтения и в одном чанке ответа.
+pub const EXPORT_PAGE_SIZE: usize = 1000;
+
+/// Страница выгрузки: строки уже в текстовом виде, по колонкам заголовка.
+pub type ExportPage = anyhow::Result<Vec<Vec<String>>>;
+
+/// Параметр `format` запроса выгрузки; остальные параметры — фильтры списка.
+#[derive(Debug, Deserialize)]
+pub struct ExportFormatQuery {
+    pub format: Option<String>,
+}
+
+impl ExportFormatQuery {
+    /// Пока поддерживается только `csv` (он же по умолчанию).
+    pub fn ensure_csv(&self) -> Result<(), (StatusCode, String)> {
+        match self.format.as_deref().map(str::trim) {
+            None | Some("") | Some("csv") => Ok(()),
+            Some(other) => Err((
+                StatusCode::BAD_REQUEST,
+                format!("Unsupported export format: {}", other),
+            )),
+        }
+    }
+}
+
+/// Сумма с двумя знаками и запятой.
+pub fn decimal(value: f64) -> String {
+    format!("{value:.2}").replace('.', ",")
+}
+
+pub fn decimal_opt(value: Option<f64>) -> String {
+    value.map(decimal).unwrap_or_default()
+}
+
+/// Кодирует строки в CSV; кавычки и переводы строк экранирует `csv`.
+pub fn encode_rows(rows: &[Vec<String>]) -> io::Result<Vec<u8>> {
+    let mut writer = ::csv::WriterBuilder::new()
+        .delimiter(b';')
+        .from_writer(Vec::new());
+    for row in rows {
+        writer.write_record(row)?;
+    }
+    writer.into_inner().map_err(|e| e.into_error())
+}
+
+fn header_chunk<H: AsRef<str>>(headers: &[H]) -> io::Result<Vec<u8>> {
+    // UTF-8 BOM — корректная кириллица при открытии в Excel.
+    let mut chunk = "\u{FEFF}".as_bytes().to_vec();
+    chunk.extend(encode_rows(&[headers
+        .iter()
+        .map(|h| h.as_ref().to_string())
+        .collect()])?);
+    Ok(chunk)
+}
+
+/// Постраничное чтение: `fetch(offset, limit)` вызывается, пока страница полная.
+pub fn paged<F, Fut>(fetch: F) -> impl Stream<Item = ExportPage> + Send + 'static
+where
+    F: Fn(usize, usize) -> Fut + Send + 'static,
+    Fut: Future<Output = ExportPage> + Send + 'static,
+{
+    stream::unfold(Some(0usize), move |offset| {
+        let next_page = offset.map(|offset| (offset, fetch(offset, EXPORT_PAGE_SIZE)));
+        async move {
+            let (offset, page) = next_page?;
+            match page.await {
+                Ok(rows) => {
+                    let next = (rows.len() == EXPORT_PAGE_SIZE).then_some(offset + rows.len());
+                    Some((Ok(rows), next))
+                }
+                Err(e) => Some((Err(e), None)),
+            }
+        }
+    })
+}
+
+/// Для источников, которые фильтруют в памяти: готовые строки режутся на чанки.
+pub fn from_rows(rows: Vec<Vec<String>>) -> impl Stream<Item = ExportPage> + Send + 'static {
+    let pages: Vec<ExportPage> = rows
+        .chunks(EXPORT_PAGE_SIZE)
+        .map(|chunk| Ok(chunk.to_vec()))
+        .collect();
+    stream::iter(pages)
+}
+
+/// Имя файла для `Content-Disposition`: части берутся из параметров запроса,
+/// поэтому всё, кроме `[A-Za-z0-9._-]`, заменяется на `_`.
+fn safe_filename(filename: &str) -> String {
+    filename
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Ответ с CSV: заголовок, затем по чанку на страницу.
+pub fn csv_response<S>(filename: &str, headers: &'static [&'static str], pages: S) -> Response
+where
+    S: Stream<Item = ExportPage> + Send + 'static,
+{
+    csv_response_with_headers(
+        filename,
+        headers.iter().map(|h| h.to_string()).collect(),
+        pages,
+    )
+}
+
+/// Как [`csv_response`], но с заголовками, собранными во время запроса
+/// (например, с вычисляемыми колонками списка).
+pub fn csv_response_with_headers<S>(filename: &str, headers: Vec<String>, pages: S) -> Response
+where
+    S: Stream<Item = ExportPage> + Send + 'static,
+{
+    let filename = safe_filename(filename);
+    let log_name = filename.clone();
+    let head = stream::once(async move { header_chunk(&headers) });
+    let rows = pages.map(move |page| match page {
+        Ok(rows) => encode_rows(&rows),
+        Err(e) => {
+            tracing::error!("CSV export {} aborted: {}", log_name, e);
+            Err(io::Error::other(e.to_string()))
+        }
+    });
+    let body = Body::from_stream(head.chain(rows).map(|chunk| chunk.map(Bytes::from)));
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_semicolon_rows_with_quoting_and_decimal_comma() {
+        let rows = vec![vec![
+            "WB;1".to_string(),
+            "Кружка \"большая\"".to_string(),
+            decimal(1234.5),
+            decimal_opt(None),
+        ]];
+        let encoded = String::from_utf8(encode_rows(&rows).unwrap()).unwrap();
+        assert_eq!(encoded, "\"WB;1\";\"Кружка \"\"большая\"\"\";1234,50;\n");
+    }
+
+    #[test]
+    fn header_chunk_starts_with_bom() {
+        let chunk = String::from_utf8(header_chunk(&["Дата", "Сумма"]).unwrap()).unwrap();
+        assert_eq!(chunk, "\u{FEFF}Дата;Сумма\n");
+    }
+
+    #[test]
+    fn rejects_unknown_format() {
+        let query = ExportFormatQuery {
+            format: Some("xlsx".to_string()),
+        };
+        assert!(query.ensure_csv().is_err());
+        assert!(ExportFormatQuery { format: None }.ensure_csv().is_ok());
+    }
+
+    #[test]
+    fn filename_keeps_only_safe_characters() {
+        assert_eq!(
+            safe_filename("wb_sales_2025-01-01_\"x\";.csv"),
+            "wb_sales_2025-01-01__x__.csv"
+        );
+    }
+}
@@ -0,0 +1,5 @@
+//! Выгрузка списков в файлы (`GET /api/{aggregate}/export?format=csv&...`)
+//! и кодирование файлов фоновых выгрузок.
+
+pub mod csv;
+pub mod xlsx;
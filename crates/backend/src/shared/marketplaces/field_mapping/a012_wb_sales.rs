@@ -0,0 +1,44 @@
+//! WB Statistics API `/api/v1/supplier/sales` → a012_wb_sales.
+//! Ключи документа (`srid`, `saleID`) разбираются в процессоре — от них зависит дедупликация.
+
+use super::FieldKind::{Bool, Float, Int, Str};
+use super::{FieldMapping as F, MappingSet};
+
+pub static MAPPING: MappingSet = MappingSet {
+    name: "a012_wb_sales",
+    version: 1,
+    fields: &[
+        // line
+        F::new("supplier_article", "supplierArticle", Str, 1),
+        F::new("nm_id", "nmId", Int, 1),
+        F::new("barcode", "barcode", Str, 1),
+        F::new("name", "brand", Str, 1),
+        F::new("qty", "quantity", Int, 1),
+        F::new("price_list", "priceWithDisc", Float, 1),
+        F::new("discount_total", "discount", Float, 1),
+        F::new("price_effective", "priceWithDisc", Float, 1),
+        F::new("amount_line", "forPay", Float, 1),
+        F::new("total_price", "totalPrice", Float, 1),
+        F::new("payment_sale_amount", "paymentSaleAmount", Float, 1),
+        F::new("discount_percent", "discountPercent", Float, 1),
+        F::new("spp", "spp", Float, 1),
+        F::new("finished_price", "finishedPrice", Float, 1),
+        // state
+        F::new("sale_dt", "date", Str, 1),
+        F::new("last_change_dt", "lastChangeDate", Str, 1),
+        F::new("is_supply", "isSupply", Bool, 1),
+        F::new("is_realization", "isRealization", Bool, 1),
+        // warehouse
+        F::new("warehouse_name", "warehouseName", Str, 1),
+        F::new("warehouse_type", "warehouseType", Str, 1),
+    ],
+    unmapped: &[
+        "srid",
+        "saleID",
+        "odid",
+        "subject",
+        "category",
+        "countryName",
+        "oblastOkrugName",
+    ],
+};
@@ -0,0 +1,48 @@
+//! Ozon `/v3/finance/transaction/list` (operations) → a014_ozon_transactions.
+//! Ключ документа (`operation_id`) разбирается в процессоре — по нему выполняется upsert.
+
+use super::FieldKind::{Float, Int, List, Str};
+use super::{FieldMapping as F, MappingSet};
+
+static ITEM: MappingSet = MappingSet {
+    name: "a014_ozon_transactions.items",
+    version: 1,
+    fields: &[F::new("name", "name", Str, 1), F::new("sku", "sku", Int, 1)],
+    unmapped: &[],
+};
+
+static SERVICE: MappingSet = MappingSet {
+    name: "a014_ozon_transactions.services",
+    version: 1,
+    fields: &[
+        F::new("name", "name", Str, 1),
+        F::new("price", "price", Float, 1),
+    ],
+    unmapped: &[],
+};
+
+pub static MAPPING: MappingSet = MappingSet {
+    name: "a014_ozon_transactions",
+    version: 1,
+    fields: &[
+        // header
+        F::new("operation_type", "operation_type", Str, 1),
+        F::new("operation_date", "operation_date", Str, 1),
+        F::new("operation_type_name", "operation_type_name", Str, 1),
+        F::new("delivery_charge", "delivery_charge", Float, 1),
+        F::new("return_delivery_charge", "return_delivery_charge", Float, 1),
+        F::new("accruals_for_sale", "accruals_for_sale", Float, 1),
+        F::new("sale_commission", "sale_commission", Float, 1),
+        F::new("amount", "amount", Float, 1),
+        F::new("transaction_type", "type", Str, 1),
+        // posting
+        F::new("delivery_schema", "posting.delivery_schema", Str, 1),
+        F::new("order_date", "posting.order_date", Str, 1),
+        F::new("posting_number", "posting.posting_number", Str, 1),
+        F::new("warehouse_id", "posting.warehouse_id", Int, 1),
+        // items / services
+        F::new("items", "items", List(&ITEM), 1),
+        F::new("services", "services", List(&SERVICE), 1),
+    ],
+    unmapped: &["operation_id"],
+};
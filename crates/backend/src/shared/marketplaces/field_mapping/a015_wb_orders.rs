@@ -0,0 +1,43 @@
+//! WB Statistics API `/api/v1/supplier/orders` → a015_wb_orders.
+//! Номер документа (`srid`) разбирается в процессоре — по нему ищется существующий документ.
+
+use super::FieldKind::{Bool, Float, Int, Str};
+use super::{FieldMapping as F, MappingSet};
+
+pub static MAPPING: MappingSet = MappingSet {
+    name: "a015_wb_orders",
+    version: 1,
+    fields: &[
+        // line
+        F::new("supplier_article", "supplierArticle", Str, 1),
+        F::new("nm_id", "nmId", Int, 1),
+        F::new("barcode", "barcode", Str, 1),
+        F::new("category", "category", Str, 1),
+        F::new("subject", "subject", Str, 1),
+        F::new("brand", "brand", Str, 1),
+        F::new("tech_size", "techSize", Str, 1),
+        F::new("total_price", "totalPrice", Float, 1),
+        F::new("discount_percent", "discountPercent", Float, 1),
+        F::new("spp", "spp", Float, 1),
+        F::new("finished_price", "finishedPrice", Float, 1),
+        F::new("price_with_disc", "priceWithDisc", Float, 1),
+        // state
+        F::new("order_dt", "date", Str, 1),
+        F::new("last_change_dt", "lastChangeDate", Str, 1),
+        F::new("is_cancel", "isCancel", Bool, 1),
+        F::new("cancel_dt", "cancelDate", Str, 1),
+        F::new("is_supply", "isSupply", Bool, 1),
+        F::new("is_realization", "isRealization", Bool, 1),
+        // warehouse / geography
+        F::new("warehouse_name", "warehouseName", Str, 1),
+        F::new("warehouse_type", "warehouseType", Str, 1),
+        F::new("country_name", "countryName", Str, 1),
+        F::new("oblast_okrug_name", "oblastOkrugName", Str, 1),
+        F::new("region_name", "regionName", Str, 1),
+        // source meta
+        F::new("income_id", "incomeID", Int, 1),
+        F::new("sticker", "sticker", Str, 1),
+        F::new("g_number", "gNumber", Str, 1),
+    ],
+    unmapped: &["srid"],
+};
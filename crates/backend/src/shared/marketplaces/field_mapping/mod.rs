@@ -0,0 +1,346 @@
+//! Декларативное сопоставление полей payload маркетплейса с полями DTO агрегатов.
+//!
+//! Для каждого агрегата задаётся таблица `MappingSet`: целевое поле DTO ← путь в
+//! JSON-строке ответа API, тип значения и версия таблицы, в которой поле появилось.
+//! Процессоры импорта читают значения через `MappedPayload` по имени целевого поля,
+//! поэтому новое поле WB/Ozon добавляется строкой в таблице (плюс поле DTO и миграция),
+//! без правки разбора в API-клиенте и процессоре. Версия таблицы сохраняется в
+//! `source_meta.document_version` документа — по ней видно, какие документы
+//! импортированы старым сопоставлением и требуют перезагрузки.
+
+pub mod a012_wb_sales;
+pub mod a014_ozon_transactions;
+pub mod a015_wb_orders;
+
+use serde_json::Value;
+
+use super::parse::{parse_integer, parse_number, ExpectedValue, FieldParseError};
+
+/// Тип значения поля.
+#[derive(Debug, Clone, Copy)]
+pub enum FieldKind {
+    Str,
+    Int,
+    Float,
+    Bool,
+    /// Массив вложенных объектов со своей таблицей сопоставления
+    List(&'static MappingSet),
+}
+
+/// Одна строка таблицы: целевое поле DTO ← путь в payload.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldMapping {
+    /// Имя поля DTO (snake_case), по которому процессор читает значение
+    pub target: &'static str,
+    /// Путь в JSON через точку: `priceWithDisc`, `posting.posting_number`
+    pub source: &'static str,
+    pub kind: FieldKind,
+    /// Версия таблицы, в которой поле появилось
+    pub since: u32,
+}
+
+impl FieldMapping {
+    pub const fn new(
+        target: &'static str,
+        source: &'static str,
+        kind: FieldKind,
+        since: u32,
+    ) -> Self {
+        Self {
+            target,
+            source,
+            kind,
+            since,
+        }
+    }
+}
+
+/// Таблица сопоставления полей для одного источника.
+#[derive(Debug)]
+pub struct MappingSet {
+    pub name: &'static str,
+    /// Текущая версия: максимум `since` по полям, растёт при каждом изменении таблицы
+    pub version: u32,
+    pub fields: &'static [FieldMapping],
+    /// Известные пути payload вне таблицы: ключи документа, которые разбирает
+    /// процессор, и поля, сознательно не переносимые в DTO. Контроль схемы
+    /// (`schema_guard`) не считает их новыми и ждёт их в ответе.
+    pub unmapped: &'static [&'static str],
+}
+
+impl MappingSet {
+    pub fn field(&self, target: &str) -> Option<&FieldMapping> {
+        self.fields.iter().find(|f| f.target == target)
+    }
+
+    /// Применить таблицу к JSON-строке ответа.
+    pub fn apply<'a>(&'static self, value: &'a Value) -> MappedPayload<'a> {
+        MappedPayload { set: self, value }
+    }
+
+    /// Проверка целостности таблицы (для тестов): уникальные целевые поля,
+    /// версия равна максимальной `since`, вложенные таблицы тоже корректны.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut seen = std::collections::HashSet::new();
+        for field in self.fields {
+            if !seen.insert(field.target) {
+                return Err(format!("{}: duplicate target {}", self.name, field.target));
+            }
+            if field.since == 0 || field.since > self.version {
+                return Err(format!(
+                    "{}: field {} has since={} outside 1..={}",
+                    self.name, field.target, field.since, self.version
+                ));
+            }
+            if let FieldKind::List(child) = field.kind {
+                child.validate()?;
+            }
+        }
+        let max_since = self.fields.iter().map(|f| f.since).max().unwrap_or(0);
+        if max_since != self.version {
+            return Err(format!(
+                "{}: version {} but newest field is since {}",
+                self.name, self.version, max_since
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// JSON-строка payload, читаемая по именам целевых полей таблицы.
+#[derive(Debug, Clone, Copy)]
+pub struct MappedPayload<'a> {
+    set: &'static MappingSet,
+    value: &'a Value,
+}
+
+impl<'a> MappedPayload<'a> {
+    pub fn version(&self) -> u32 {
+        self.set.version
+    }
+
+    fn raw(&self, target: &str) -> Option<&'a Value> {
+        let Some(field) = self.set.field(target) else {
+            debug_assert!(
+                false,
+                "{}: unknown mapping target {}",
+                self.set.name, target
+            );
+            return None;
+        };
+        field
+            .source
+            .split('.')
+            .try_fold(self.value, |value, key| value.get(key))
+            .filter(|value| !value.is_null())
+    }
+
+    pub fn str(&self, target: &str) -> Option<String> {
+        match self.raw(target)? {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            Value::Bool(b) => Some(b.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Числа WB иногда приходят строкой (в т.ч. `1 234,5`) — принимаем оба варианта.
+    pub fn f64(&self, target: &str) -> Option<f64> {
+        match self.raw(target)? {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => parse_number(s),
+            _ => None,
+        }
+    }
+
+    pub fn i64(&self, target: &str) -> Option<i64> {
+        match self.raw(target)? {
+            Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|f| f as i64)),
+            Value::String(s) => parse_integer(s),
+            _ => None,
+        }
+    }
+
+    pub fn bool(&self, target: &str) -> Option<bool> {
+        match self.raw(target)? {
+            Value::Bool(b) => Some(*b),
+            Value::Number(n) => n.as_i64().map(|v| v != 0),
+            Value::String(s) => match s.trim() {
+                "true" | "1" => Some(true),
+                "false" | "0" => Some(false),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Поля, значение которых есть, но не разобрано по типу таблицы (включая
+    /// вложенные массивы: `services[2].price`). Пустая строка ошибкой не считается.
+    pub fn parse_errors(&self) -> Vec<FieldParseError> {
+        let mut errors = Vec::new();
+        self.collect_parse_errors("", &mut errors);
+        errors
+    }
+
+    fn collect_parse_errors(&self, prefix: &str, errors: &mut Vec<FieldParseError>) {
+        for field in self.set.fields {
+            let expected = match field.kind {
+                FieldKind::Str => continue,
+                FieldKind::Float => ExpectedValue::Number,
+                FieldKind::Int => ExpectedValue::Integer,
+                FieldKind::Bool => ExpectedValue::Bool,
+                FieldKind::List(_) => {
+                    for (i, item) in self.list(field.target).iter().enumerate() {
+                        item.collect_parse_errors(
+                            &format!("{}{}[{}].", prefix, field.target, i),
+                            errors,
+                        );
+                    }
+                    continue;
+                }
+            };
+            let Some(raw) = self.raw(field.target) else {
+                continue;
+            };
+            if matches!(raw, Value::String(s) if s.trim().is_empty()) {
+                continue;
+            }
+            let parsed = match expected {
+                ExpectedValue::Number => self.f64(field.target).is_some(),
+                ExpectedValue::Integer => self.i64(field.target).is_some(),
+                _ => self.bool(field.target).is_some(),
+            };
+            if !parsed {
+                errors.push(FieldParseError {
+                    field: format!("{}{}", prefix, field.target),
+                    raw: match raw {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    },
+                    expected,
+                });
+            }
+        }
+    }
+
+    /// Элементы вложенного массива, каждый со своей таблицей.
+    pub fn list(&self, target: &str) -> Vec<MappedPayload<'a>> {
+        let child = match self.set.field(target).map(|f| f.kind) {
+            Some(FieldKind::List(child)) => child,
+            _ => {
+                debug_assert!(false, "{}: {} is not a list mapping", self.set.name, target);
+                return Vec::new();
+            }
+        };
+        match self.raw(target) {
+            Some(Value::Array(items)) => items.iter().map(|item| child.apply(item)).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    static CHILD: MappingSet = MappingSet {
+        name: "test_child",
+        version: 1,
+        fields: &[FieldMapping::new("name", "name", FieldKind::Str, 1)],
+        unmapped: &[],
+    };
+
+    static SET: MappingSet = MappingSet {
+        name: "test",
+        version: 2,
+        fields: &[
+            FieldMapping::new("price", "priceWithDisc", FieldKind::Float, 1),
+            FieldMapping::new("qty", "quantity", FieldKind::Int, 1),
+            FieldMapping::new("is_supply", "isSupply", FieldKind::Bool, 1),
+            FieldMapping::new(
+                "posting_number",
+                "posting.posting_number",
+                FieldKind::Str,
+                2,
+            ),
+            FieldMapping::new("items", "items", FieldKind::List(&CHILD), 2),
+        ],
+        unmapped: &["id"],
+    };
+
+    #[test]
+    fn reads_typed_values_by_target() {
+        let value = json!({
+            "priceWithDisc": "1234.5",
+            "quantity": 2,
+            "isSupply": 1,
+            "posting": { "posting_number": "123-45" },
+            "items": [{ "name": "a" }, { "name": "b" }]
+        });
+        let payload = SET.apply(&value);
+        assert_eq!(payload.f64("price"), Some(1234.5));
+        assert_eq!(payload.i64("qty"), Some(2));
+        assert_eq!(payload.bool("is_supply"), Some(true));
+        assert_eq!(payload.str("posting_number").as_deref(), Some("123-45"));
+        let names: Vec<_> = payload
+            .list("items")
+            .iter()
+            .filter_map(|item| item.str("name"))
+            .collect();
+        assert_eq!(names, vec!["a", "b"]);
+        assert_eq!(payload.version(), 2);
+    }
+
+    #[test]
+    fn missing_and_null_values_are_none() {
+        let value = json!({ "priceWithDisc": null });
+        let payload = SET.apply(&value);
+        assert_eq!(payload.f64("price"), None);
+        assert_eq!(payload.str("posting_number"), None);
+        assert!(payload.list("items").is_empty());
+    }
+
+    #[test]
+    fn reports_unparsed_values() {
+        let value = json!({
+            "priceWithDisc": "1 234,5",
+            "quantity": "два",
+            "isSupply": "",
+            "items": [{ "name": "a" }]
+        });
+        let payload = SET.apply(&value);
+        assert_eq!(payload.f64("price"), Some(1234.5));
+        let errors = payload.parse_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "qty");
+        assert_eq!(errors[0].raw, "два");
+        assert_eq!(errors[0].expected, ExpectedValue::Integer);
+    }
+
+    #[test]
+    fn aggregate_mappings_are_consistent() {
+        for set in [
+            &a012_wb_sales::MAPPING,
+            &a014_ozon_transactions::MAPPING,
+            &a015_wb_orders::MAPPING,
+        ] {
+            assert_eq!(set.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn validate_checks_version_and_duplicates() {
+        assert!(SET.validate().is_ok());
+        static BAD: MappingSet = MappingSet {
+            name: "bad",
+            version: 1,
+            fields: &[
+                FieldMapping::new("a", "a", FieldKind::Str, 1),
+                FieldMapping::new("a", "b", FieldKind::Str, 1),
+            ],
+            unmapped: &[],
+        };
+        assert!(BAD.validate().is_err());
+    }
+}
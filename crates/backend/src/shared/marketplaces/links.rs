@@ -0,0 +1,114 @@
+//! Ссылки на страницы маркетплейсов для карточек документов.
+//!
+//! Все форматы URL собраны здесь: если маркетплейс поменяет адреса, правится
+//! одна функция, а не каждая карточка на фронтенде.
+
+use contracts::shared::marketplace_links::MarketplaceLinkDto;
+
+const WB_SITE: &str = "https://www.wildberries.ru";
+const WB_SELLER: &str = "https://seller.wildberries.ru";
+const OZON_SELLER: &str = "https://seller.ozon.ru";
+
+/// Схема работы Ozon, от неё зависит раздел кабинета с отправлениями.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OzonScheme {
+    Fbs,
+    Fbo,
+}
+
+impl OzonScheme {
+    fn path(self) -> &'static str {
+        match self {
+            Self::Fbs => "fbs",
+            Self::Fbo => "fbo",
+        }
+    }
+}
+
+fn link(title: &str, url: String) -> MarketplaceLinkDto {
+    MarketplaceLinkDto {
+        title: title.to_string(),
+        url,
+    }
+}
+
+/// Карточка товара на витрине WB по nm_id; `None` — nm_id не заполнен.
+pub fn wb_product(nm_id: i64) -> Option<MarketplaceLinkDto> {
+    (nm_id > 0).then(|| {
+        link(
+            "Товар на Wildberries",
+            format!("{}/catalog/{}/detail.aspx", WB_SITE, nm_id),
+        )
+    })
+}
+
+pub fn wb_seller_cabinet() -> MarketplaceLinkDto {
+    link("Кабинет WB", format!("{}/", WB_SELLER))
+}
+
+/// Отправление в кабинете продавца Ozon (поиск по posting_number).
+pub fn ozon_posting(posting_number: &str, scheme: OzonScheme) -> Option<MarketplaceLinkDto> {
+    let posting_number = posting_number.trim();
+    (!posting_number.is_empty()).then(|| {
+        link(
+            "Отправление в Ozon",
+            format!(
+                "{}/app/postings/{}?search={}",
+                OZON_SELLER,
+                scheme.path(),
+                urlencoding::encode(posting_number)
+            ),
+        )
+    })
+}
+
+pub fn ozon_seller_cabinet() -> MarketplaceLinkDto {
+    link("Кабинет Ozon", format!("{}/app/", OZON_SELLER))
+}
+
+/// Ссылки для документа WB (заказ, продажа): товар и кабинет.
+pub fn wb_document_links(nm_id: i64) -> Vec<MarketplaceLinkDto> {
+    wb_product(nm_id)
+        .into_iter()
+        .chain(std::iter::once(wb_seller_cabinet()))
+        .collect()
+}
+
+/// Ссылки для отправления Ozon: отправление и кабинет.
+pub fn ozon_posting_links(posting_number: &str, scheme: OzonScheme) -> Vec<MarketplaceLinkDto> {
+    ozon_posting(posting_number, scheme)
+        .into_iter()
+        .chain(std::iter::once(ozon_seller_cabinet()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wb_links_skip_missing_nm_id() {
+        let links = wb_document_links(123456789);
+        assert_eq!(
+            links[0].url,
+            "https://www.wildberries.ru/catalog/123456789/detail.aspx"
+        );
+        assert_eq!(links[1], wb_seller_cabinet());
+        assert_eq!(wb_document_links(0), vec![wb_seller_cabinet()]);
+    }
+
+    #[test]
+    fn ozon_posting_link_depends_on_scheme() {
+        assert_eq!(
+            ozon_posting("0123456789-0001-1", OzonScheme::Fbs)
+                .unwrap()
+                .url,
+            "https://seller.ozon.ru/app/postings/fbs?search=0123456789-0001-1"
+        );
+        assert_eq!(
+            ozon_posting(" 9876-1 ", OzonScheme::Fbo).unwrap().url,
+            "https://seller.ozon.ru/app/postings/fbo?search=9876-1"
+        );
+        assert_eq!(ozon_posting("  ", OzonScheme::Fbs), None);
+    }
+}
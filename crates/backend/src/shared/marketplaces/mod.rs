@@ -1,5 +1,11 @@
+pub mod field_mapping;
 pub mod lemanapro;
+pub mod links;
 pub mod ozon;
+pub mod parse;
+pub mod retry;
+pub mod sandbox;
+pub mod schema_guard;
 pub mod wildberries;
 pub mod yandex_market;
 
@@ -1,4 +1,4 @@
-use super::{MarketplaceClient, TestConnectionResult};
+use super::{sandbox, MarketplaceClient, TestConnectionResult};
 use async_trait::async_trait;
 use contracts::domain::a006_connection_mp::aggregate::ConnectionMPDto;
 
@@ -46,11 +46,20 @@ impl MarketplaceClient for OzonClient {
 
         // Используем endpoint для получения ролей пользователя
         // Это легкий метод API для проверки валидности ключей
-        let url = "https://api-seller.ozon.ru/v1/roles";
+        let url = match sandbox::resolve(dto.is_sandbox, "https://api-seller.ozon.ru/v1/roles") {
+            Ok(url) => url,
+            Err(e) => {
+                return TestConnectionResult {
+                    success: false,
+                    message: "Песочница недоступна".into(),
+                    details: Some(format!("{}", e)),
+                }
+            }
+        };
 
         // Выполняем POST запрос (большинство методов Ozon используют POST)
         let response = match client
-            .post(url)
+            .post(&url)
             .header("Client-Id", &client_id)
             .header("Api-Key", &dto.api_key)
             .header("Content-Type", "application/json")
@@ -0,0 +1,300 @@
+//! Разбор чисел и дат из входных данных импорта (CSV-отчёты, строковые поля API).
+//!
+//! Маркетплейсы отдают одно и то же по-разному: `1 234,56`, `1234.56`, `1,234.56`,
+//! `−15 ₽`, даты `2026-03-01`, `01.03.2026`, `01-03-2026 10:00:00`. Функции здесь
+//! принимают все эти варианты и возвращают `None`, если значение не разобрано —
+//! вызывающий код записывает [`FieldParseError`] в отчёт импорта вместо того,
+//! чтобы молча сохранить ноль.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use std::fmt;
+
+/// Сколько ошибок разбора перечисляется в отчёте импорта (остальные — счётчиком).
+pub const FIELD_ERRORS_REPORT_LIMIT: usize = 20;
+
+/// Число с разделителями тысяч (пробел, неразрывный пробел, апостроф, точка/запятая)
+/// и десятичной запятой или точкой. Валюта (`₽`, `руб.`, `RUB`) отбрасывается.
+pub fn parse_number(raw: &str) -> Option<f64> {
+    let mut s: String = raw
+        .trim()
+        .trim_end_matches('.')
+        .trim_end_matches("₽")
+        .trim_end_matches("руб")
+        .trim_end_matches("RUB")
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '\u{00a0}' | '\u{202f}' | '\''))
+        .map(|c| if c == '−' { '-' } else { c })
+        .collect();
+    if s.is_empty() {
+        return None;
+    }
+
+    let commas = s.matches(',').count();
+    let dots = s.matches('.').count();
+    if commas > 0 && dots > 0 {
+        // Десятичный разделитель — тот, что правее: `1,234.56` / `1.234,56`
+        let (thousands, decimal) = if s.rfind(',') > s.rfind('.') {
+            ('.', ',')
+        } else {
+            (',', '.')
+        };
+        s = s.replace(thousands, "").replace(decimal, ".");
+    } else if commas > 1 {
+        s = s.replace(',', "");
+    } else if commas == 1 {
+        s = s.replace(',', ".");
+    } else if dots > 1 {
+        s = s.replace('.', "");
+    }
+
+    s.parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+/// Целое число; дробная часть допускается только нулевая (`2,0`).
+pub fn parse_integer(raw: &str) -> Option<i64> {
+    if let Ok(v) = raw.trim().parse::<i64>() {
+        return Some(v);
+    }
+    parse_number(raw)
+        .filter(|v| v.fract() == 0.0 && v.abs() < i64::MAX as f64)
+        .map(|v| v as i64)
+}
+
+const DATE_FORMATS: [&str; 5] = ["%Y-%m-%d", "%d.%m.%Y", "%d-%m-%Y", "%d/%m/%Y", "%Y.%m.%d"];
+
+const DATETIME_FORMATS: [&str; 10] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+    "%d.%m.%Y %H:%M:%S",
+    "%d.%m.%Y %H:%M",
+    "%d-%m-%Y %H:%M:%S",
+    "%d-%m-%Y %H:%M",
+    "%d/%m/%Y %H:%M:%S",
+];
+
+/// Дата и время без часового пояса; дата без времени — полночь.
+/// У RFC3339 со смещением берётся местное время из строки.
+pub fn parse_naive_datetime(raw: &str) -> Option<NaiveDateTime> {
+    let s = raw.trim();
+    if s.is_empty() {
+        return None;
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.naive_local());
+    }
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(s, fmt).ok())
+        .or_else(|| {
+            DATE_FORMATS
+                .iter()
+                .find_map(|fmt| NaiveDate::parse_from_str(s, fmt).ok())
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+}
+
+/// Календарная дата из даты или даты-времени в любом из поддерживаемых форматов.
+pub fn parse_date(raw: &str) -> Option<NaiveDate> {
+    let s = raw.trim();
+    DATE_FORMATS
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(s, fmt).ok())
+        .or_else(|| parse_naive_datetime(s).map(|dt| dt.date()))
+}
+
+/// Ожидаемый тип значения поля — для текста ошибки.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectedValue {
+    Number,
+    Integer,
+    Date,
+    Bool,
+}
+
+impl ExpectedValue {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Number => "число",
+            Self::Integer => "целое число",
+            Self::Date => "дата",
+            Self::Bool => "да/нет",
+        }
+    }
+}
+
+/// Значение поля присутствует, но не разобрано.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldParseError {
+    /// Имя поля (колонка отчёта или целевое поле DTO)
+    pub field: String,
+    pub raw: String,
+    pub expected: ExpectedValue,
+}
+
+impl fmt::Display for FieldParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: «{}» — ожидается {}",
+            self.field,
+            self.raw,
+            self.expected.label()
+        )
+    }
+}
+
+/// Разбирает поле и копит ошибки для отчёта импорта.
+/// Пустое/отсутствующее значение — не ошибка (`None` без записи).
+#[derive(Debug, Default)]
+pub struct FieldErrors {
+    errors: Vec<FieldParseError>,
+}
+
+impl FieldErrors {
+    fn check<T>(
+        &mut self,
+        field: &str,
+        raw: Option<&str>,
+        expected: ExpectedValue,
+        parse: impl Fn(&str) -> Option<T>,
+    ) -> Option<T> {
+        let raw = raw.map(str::trim).filter(|v| !v.is_empty())?;
+        let value = parse(raw);
+        if value.is_none() {
+            self.errors.push(FieldParseError {
+                field: field.to_string(),
+                raw: raw.to_string(),
+                expected,
+            });
+        }
+        value
+    }
+
+    pub fn number(&mut self, field: &str, raw: Option<&str>) -> Option<f64> {
+        self.check(field, raw, ExpectedValue::Number, parse_number)
+    }
+
+    pub fn integer(&mut self, field: &str, raw: Option<&str>) -> Option<i64> {
+        self.check(field, raw, ExpectedValue::Integer, parse_integer)
+    }
+
+    pub fn date(&mut self, field: &str, raw: Option<&str>) -> Option<NaiveDate> {
+        self.check(field, raw, ExpectedValue::Date, parse_date)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Добавить ошибки строки; `context` (номер документа, строка отчёта)
+    /// дописывается к имени поля, чтобы ошибку можно было найти в источнике.
+    pub fn extend(&mut self, context: &str, errors: Vec<FieldParseError>) {
+        self.errors.extend(errors.into_iter().map(|mut e| {
+            e.field = format!("{} / {}", context, e.field);
+            e
+        }));
+    }
+
+    pub fn into_vec(self) -> Vec<FieldParseError> {
+        self.errors
+    }
+}
+
+/// Сообщение и детали для отчёта импорта: `None`, если ошибок нет.
+/// В детали попадают первые [`FIELD_ERRORS_REPORT_LIMIT`] ошибок.
+pub fn report_summary(errors: &[FieldParseError]) -> Option<(String, String)> {
+    if errors.is_empty() {
+        return None;
+    }
+    let mut details: Vec<String> = errors
+        .iter()
+        .take(FIELD_ERRORS_REPORT_LIMIT)
+        .map(ToString::to_string)
+        .collect();
+    if errors.len() > FIELD_ERRORS_REPORT_LIMIT {
+        details.push(format!(
+            "… и ещё {}",
+            errors.len() - FIELD_ERRORS_REPORT_LIMIT
+        ));
+    }
+    Some((
+        format!("Не разобраны значения полей: {}", errors.len()),
+        details.join("\n"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_number_formats() {
+        assert_eq!(parse_number("1234.56"), Some(1234.56));
+        assert_eq!(parse_number("1234,56"), Some(1234.56));
+        assert_eq!(parse_number("1 234,56"), Some(1234.56));
+        assert_eq!(parse_number("1\u{00a0}234,56"), Some(1234.56));
+        assert_eq!(parse_number("1\u{202f}234\u{202f}567"), Some(1234567.0));
+        assert_eq!(parse_number("1,234.56"), Some(1234.56));
+        assert_eq!(parse_number("1.234,56"), Some(1234.56));
+        assert_eq!(parse_number("1.234.567"), Some(1234567.0));
+        assert_eq!(parse_number("−15"), Some(-15.0));
+        assert_eq!(parse_number("-15,5 ₽"), Some(-15.5));
+        assert_eq!(parse_number("100 руб."), Some(100.0));
+    }
+
+    #[test]
+    fn rejects_non_numbers() {
+        assert_eq!(parse_number(""), None);
+        assert_eq!(parse_number("   "), None);
+        assert_eq!(parse_number("н/д"), None);
+        assert_eq!(parse_number("12abc"), None);
+        assert_eq!(parse_number("NaN"), None);
+        assert_eq!(parse_integer("2,5"), None);
+        assert_eq!(parse_integer("2,0"), Some(2));
+        assert_eq!(parse_integer("1 000"), Some(1000));
+    }
+
+    #[test]
+    fn parses_date_formats() {
+        let d = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        for raw in [
+            "2026-03-01",
+            "01.03.2026",
+            "01-03-2026",
+            "01/03/2026",
+            "2026-03-01T10:15:00",
+            "2026-03-01 10:15:00",
+            "01.03.2026 10:15",
+            "01-03-2026 10:15:00",
+            "2026-03-01T10:15:00+03:00",
+        ] {
+            assert_eq!(parse_date(raw), Some(d), "{}", raw);
+        }
+        assert_eq!(parse_date("2026-13-01"), None);
+        assert_eq!(parse_date("вчера"), None);
+    }
+
+    #[test]
+    fn field_errors_skip_empty_and_record_bad_values() {
+        let mut errors = FieldErrors::default();
+        assert_eq!(errors.number("SUM", None), None);
+        assert_eq!(errors.number("SUM", Some("  ")), None);
+        assert!(errors.is_empty());
+        assert_eq!(errors.number("SUM", Some("1 000,5")), Some(1000.5));
+        assert_eq!(errors.number("SUM", Some("—")), None);
+        assert_eq!(errors.date("DATE", Some("32.01.2026")), None);
+
+        let errors = errors.into_vec();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].field, "SUM");
+        assert_eq!(errors[1].expected, ExpectedValue::Date);
+
+        let (message, details) = report_summary(&errors).unwrap();
+        assert_eq!(message, "Не разобраны значения полей: 2");
+        assert_eq!(details.lines().count(), 2);
+        assert!(report_summary(&[]).is_none());
+    }
+}
@@ -0,0 +1,204 @@
+//! Повтор запросов к API маркетплейсов с экспоненциальной паузой.
+//!
+//! Повторяем только временные сбои: 429, 408, 500/502/503/504, таймаут и обрыв
+//! соединения. Пауза — `Retry-After` (секунды или HTTP-дата) либо WB-шный
+//! `X-Ratelimit-Retry`, а если сервер молчит — `base_delay · 2^(n-1)` с разбросом
+//! ±50 %, не больше `max_delay`. Когда попытки кончились или сервер просит ждать
+//! дольше `max_retry_after`, возвращается последний ответ/ошибка как есть —
+//! разбор статуса остаётся у вызывающего кода.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{RequestBuilder, Response, StatusCode};
+
+use crate::shared::config::get_http_retry_config;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Всего попыток, включая первую
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub max_retry_after: Duration,
+}
+
+impl RetryPolicy {
+    /// Политика из `[http_retry]` config.toml.
+    pub fn from_config() -> Self {
+        let cfg = get_http_retry_config();
+        Self {
+            max_attempts: cfg.max_attempts.max(1),
+            base_delay: Duration::from_millis(cfg.base_delay_ms),
+            max_delay: Duration::from_secs(cfg.max_delay_secs),
+            max_retry_after: Duration::from_secs(cfg.max_retry_after_secs),
+        }
+    }
+
+    /// Пауза перед повтором номер `retry` (с 1) без подсказки сервера.
+    /// `jitter` — случайное число из `[0, 1)`.
+    pub fn backoff(&self, retry: u32, jitter: f64) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)));
+        let capped = exp.min(self.max_delay);
+        capped.mul_f64(0.5 + jitter.clamp(0.0, 1.0))
+    }
+}
+
+/// Повтор, о котором сообщаем вызывающему коду (журнал, счётчики импорта).
+#[derive(Debug, Clone)]
+pub struct RetryEvent {
+    /// Номер повтора, с 1
+    pub retry: u32,
+    pub max_attempts: u32,
+    pub delay: Duration,
+    /// `HTTP 429`, `HTTP 503`, текст ошибки соединения
+    pub reason: String,
+}
+
+/// Сколько сервер просит подождать: `Retry-After` или `X-Ratelimit-Retry` (WB).
+pub fn server_retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+    };
+    if let Some(value) = header(RETRY_AFTER.as_str()) {
+        if let Ok(secs) = value.parse::<u64>() {
+            return Some(Duration::from_secs(secs));
+        }
+        if let Ok(at) = DateTime::parse_from_rfc2822(value) {
+            let secs = (at.with_timezone(&Utc) - now).num_seconds().max(0);
+            return Some(Duration::from_secs(secs as u64));
+        }
+    }
+    header("X-Ratelimit-Retry")
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::REQUEST_TIMEOUT
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+fn is_retryable_error(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_connect()
+}
+
+/// Отправляет запрос, повторяя его по `policy` при временных сбоях.
+/// `request` собирает запрос заново на каждую попытку; `on_retry` вызывается
+/// перед каждой паузой.
+pub async fn send_with_retry(
+    policy: &RetryPolicy,
+    label: &str,
+    mut request: impl FnMut() -> RequestBuilder,
+    mut on_retry: impl FnMut(&RetryEvent),
+) -> Result<Response, reqwest::Error> {
+    let mut attempt = 1;
+    loop {
+        let result = request().send().await;
+        let (reason, server_wait) = match &result {
+            Ok(response) if is_retryable_status(response.status()) => (
+                format!("HTTP {}", response.status().as_u16()),
+                server_retry_after(response.headers(), Utc::now()),
+            ),
+            Err(err) if is_retryable_error(err) => (err.to_string(), None),
+            _ => return result,
+        };
+        if attempt >= policy.max_attempts {
+            return result;
+        }
+        let delay = match server_wait {
+            Some(wait) if wait > policy.max_retry_after => return result,
+            Some(wait) => wait,
+            None => policy.backoff(attempt, rand::random::<f64>()),
+        };
+
+        let event = RetryEvent {
+            retry: attempt,
+            max_attempts: policy.max_attempts,
+            delay,
+            reason,
+        };
+        tracing::warn!(
+            "{}: {}, retry {}/{} in {:.1}s",
+            label,
+            event.reason,
+            event.retry,
+            event.max_attempts - 1,
+            delay.as_secs_f64()
+        );
+        on_retry(&event);
+        drop(result);
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(10),
+            max_retry_after: Duration::from_secs(300),
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_with_cap_and_jitter() {
+        let p = policy();
+        assert_eq!(p.backoff(1, 0.5), Duration::from_secs(2));
+        assert_eq!(p.backoff(2, 0.5), Duration::from_secs(4));
+        assert_eq!(p.backoff(3, 0.5), Duration::from_secs(8));
+        assert_eq!(p.backoff(4, 0.5), Duration::from_secs(10));
+        assert_eq!(p.backoff(30, 0.5), Duration::from_secs(10));
+        assert_eq!(p.backoff(1, 0.0), Duration::from_secs(1));
+        assert_eq!(p.backoff(2, 1.0), Duration::from_secs(6));
+    }
+
+    #[test]
+    fn reads_retry_after_seconds_date_and_wb_header() {
+        let now = DateTime::parse_from_rfc3339("2025-11-05T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut headers = HeaderMap::new();
+        assert_eq!(server_retry_after(&headers, now), None);
+
+        headers.insert("X-Ratelimit-Retry", HeaderValue::from_static("7"));
+        assert_eq!(
+            server_retry_after(&headers, now),
+            Some(Duration::from_secs(7))
+        );
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 05 Nov 2025 10:01:30 GMT"),
+        );
+        assert_eq!(
+            server_retry_after(&headers, now),
+            Some(Duration::from_secs(90))
+        );
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static(" 12 "));
+        assert_eq!(
+            server_retry_after(&headers, now),
+            Some(Duration::from_secs(12))
+        );
+    }
+}
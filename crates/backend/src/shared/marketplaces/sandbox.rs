@@ -0,0 +1,111 @@
+//! Песочница для подключений в тестовом режиме (`ConnectionMP::is_sandbox`).
+//!
+//! API-клиенты пропускают каждый URL через [`endpoint`]: для боевых подключений
+//! он не меняется, для тестовых — уходит на mock-сервер из `[sandbox]` config.toml
+//! или на sandbox-хост WB. Для Ozon и Яндекс.Маркета песочницы нет, поэтому без
+//! mock-сервера запрос отклоняется — тестовый токен не должен попасть в боевой API.
+
+use anyhow::Result;
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+
+/// Сервисы WB, у которых есть `*-sandbox.wildberries.ru`.
+const WB_SANDBOX_SERVICES: &[&str] = &[
+    "content-api",
+    "discounts-prices-api",
+    "statistics-api",
+    "advert-api",
+    "feedbacks-api",
+];
+
+/// Хосты маркетплейсов: на них тестовое подключение без песочницы не ходит.
+const MARKETPLACE_HOST_SUFFIXES: &[&str] = &[".wildberries.ru", ".ozon.ru", ".market.yandex.ru"];
+
+/// URL запроса с учётом тестового режима подключения.
+pub fn endpoint(connection: &ConnectionMP, url: &str) -> Result<String> {
+    resolve(connection.is_sandbox, url)
+}
+
+/// То же по флагу — для проверки подключения по DTO формы (ещё не сохранённой).
+pub fn resolve(is_sandbox: bool, url: &str) -> Result<String> {
+    if !is_sandbox {
+        return Ok(url.to_string());
+    }
+    let mock_base_url = crate::shared::config::get_sandbox_config()
+        .mock_base_url
+        .trim();
+    sandbox_url(url, Some(mock_base_url).filter(|s| !s.is_empty())).map_err(|e| anyhow::anyhow!(e))
+}
+
+/// Импорт пишет прямо в проекцию (p9xx) — для песочницы такой импорт пропускается.
+pub fn skips_import(connection: &ConnectionMP, aggregate_index: &str) -> bool {
+    connection.is_sandbox && aggregate_index.starts_with('p')
+}
+
+fn sandbox_url(url: &str, mock_base_url: Option<&str>) -> Result<String, String> {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return Err(format!("Некорректный URL: {}", url));
+    };
+    let host_end = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (host, path) = rest.split_at(host_end);
+
+    // Собственный адрес (например, base URL в поле «ID поставщика») — уже не боевой API
+    if !MARKETPLACE_HOST_SUFFIXES.iter().any(|s| host.ends_with(s)) {
+        return Ok(url.to_string());
+    }
+    if let Some(mock) = mock_base_url {
+        return Ok(format!("{}/{}{}", mock.trim_end_matches('/'), host, path));
+    }
+    if let Some(service) = host.strip_suffix(".wildberries.ru") {
+        if WB_SANDBOX_SERVICES.contains(&service) {
+            return Ok(format!(
+                "{}://{}-sandbox.wildberries.ru{}",
+                scheme, service, path
+            ));
+        }
+    }
+    Err(format!(
+        "Подключение в режиме песочницы: для {} нет sandbox-эндпоинта, задайте [sandbox].mock_base_url в config.toml",
+        host
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wb_host_goes_to_sandbox() {
+        assert_eq!(
+            sandbox_url(
+                "https://statistics-api.wildberries.ru/api/v1/supplier/sales",
+                None
+            ),
+            Ok("https://statistics-api-sandbox.wildberries.ru/api/v1/supplier/sales".to_string())
+        );
+    }
+
+    #[test]
+    fn mock_server_keeps_host_and_query() {
+        assert_eq!(
+            sandbox_url(
+                "https://api-seller.ozon.ru/v3/product/list?x=1",
+                Some("http://localhost:8090/")
+            ),
+            Ok("http://localhost:8090/api-seller.ozon.ru/v3/product/list?x=1".to_string())
+        );
+    }
+
+    #[test]
+    fn marketplace_without_sandbox_is_rejected() {
+        assert!(sandbox_url("https://api.partner.market.yandex.ru/campaigns", None).is_err());
+        assert!(sandbox_url(
+            "https://dp-calendar-api.wildberries.ru/api/v1/calendar/promotions",
+            None
+        )
+        .is_err());
+        assert_eq!(
+            sandbox_url("http://127.0.0.1:9000/content/v2/get/cards/list", None),
+            Ok("http://127.0.0.1:9000/content/v2/get/cards/list".to_string())
+        );
+    }
+}
@@ -0,0 +1,304 @@
+//! Контроль схемы ответов маркетплейсов.
+//!
+//! Страница ответа сверяется с таблицей сопоставления полей (`field_mapping`):
+//! - `unknown_field` — в строках есть поле, которого нет ни в таблице, ни в
+//!   `unmapped`: маркетплейс добавил данные, которые импорт не переносит;
+//! - `missing_field` — известного поля нет ни в одной строке страницы
+//!   (`null` считается присутствием): поле переименовано или удалено;
+//! - `row_rejected` — строка не разобрана в структуру клиента. Такая строка
+//!   пропускается, остальные страницы импортируются, а не падает весь импорт.
+//!
+//! Расхождения пишутся в `sys_schema_drift` по эндпоинту с примером значения и
+//! попадают в проверку качества `schema_drift`, где их принимает администратор.
+
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::field_mapping::{FieldKind, MappingSet};
+
+/// Длина примера значения в записи о расхождении.
+const EXAMPLE_MAX_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SchemaDriftKind {
+    UnknownField,
+    MissingField,
+    RowRejected,
+}
+
+impl SchemaDriftKind {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::UnknownField => "unknown_field",
+            Self::MissingField => "missing_field",
+            Self::RowRejected => "row_rejected",
+        }
+    }
+}
+
+/// Одно расхождение на странице ответа.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaDrift {
+    pub kind: SchemaDriftKind,
+    /// Путь поля (`items[].name`) или текст ошибки разбора строки
+    pub field: String,
+    pub example: Option<String>,
+    /// Сколько раз встретилось: страниц для полей, строк для `row_rejected`
+    pub count: i64,
+}
+
+fn example(value: &Value) -> String {
+    let text = match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if text.chars().count() > EXAMPLE_MAX_CHARS {
+        let cut: String = text.chars().take(EXAMPLE_MAX_CHARS).collect();
+        format!("{cut}…")
+    } else {
+        text
+    }
+}
+
+/// Значение по пути через точку; в отличие от `MappedPayload`, `null` — найдено.
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |value, key| value.get(key))
+}
+
+fn walk_unknown(
+    set: &MappingSet,
+    rel_prefix: &str,
+    display_prefix: &str,
+    value: &Value,
+    out: &mut BTreeMap<String, String>,
+) {
+    let Some(object) = value.as_object() else {
+        return;
+    };
+    for (key, child) in object {
+        let path = if rel_prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{rel_prefix}.{key}")
+        };
+        if let Some(field) = set.fields.iter().find(|f| f.source == path) {
+            if let (FieldKind::List(child_set), Value::Array(items)) = (field.kind, child) {
+                let item_prefix = format!("{display_prefix}{path}[].");
+                for item in items {
+                    walk_unknown(child_set, "", &item_prefix, item, out);
+                }
+            }
+            continue;
+        }
+        if set.unmapped.contains(&path.as_str()) {
+            continue;
+        }
+        let nested = format!("{path}.");
+        let is_known_parent = set
+            .fields
+            .iter()
+            .map(|f| f.source)
+            .chain(set.unmapped.iter().copied())
+            .any(|known| known.starts_with(&nested));
+        if is_known_parent && child.is_object() {
+            walk_unknown(set, &path, display_prefix, child, out);
+        } else {
+            out.entry(format!("{display_prefix}{path}"))
+                .or_insert_with(|| example(child));
+        }
+    }
+}
+
+fn collect_missing(
+    set: &MappingSet,
+    display_prefix: &str,
+    values: &[&Value],
+    out: &mut Vec<String>,
+) {
+    if values.is_empty() {
+        return;
+    }
+    for field in set.fields {
+        if !values.iter().any(|v| lookup(v, field.source).is_some()) {
+            out.push(format!("{display_prefix}{}", field.source));
+            continue;
+        }
+        if let FieldKind::List(child) = field.kind {
+            let items: Vec<&Value> = values
+                .iter()
+                .filter_map(|v| lookup(v, field.source).and_then(Value::as_array))
+                .flatten()
+                .collect();
+            collect_missing(
+                child,
+                &format!("{display_prefix}{}[].", field.source),
+                &items,
+                out,
+            );
+        }
+    }
+    for path in set.unmapped {
+        if !values.iter().any(|v| lookup(v, path).is_some()) {
+            out.push(format!("{display_prefix}{path}"));
+        }
+    }
+}
+
+/// Новые и пропавшие поля страницы относительно таблицы сопоставления.
+pub fn detect(set: &MappingSet, rows: &[Value]) -> Vec<SchemaDrift> {
+    let mut unknown = BTreeMap::new();
+    for row in rows {
+        walk_unknown(set, "", "", row, &mut unknown);
+    }
+    let mut missing = Vec::new();
+    collect_missing(set, "", &rows.iter().collect::<Vec<_>>(), &mut missing);
+
+    unknown
+        .into_iter()
+        .map(|(field, example)| SchemaDrift {
+            kind: SchemaDriftKind::UnknownField,
+            field,
+            example: Some(example),
+            count: 1,
+        })
+        .chain(missing.into_iter().map(|field| SchemaDrift {
+            kind: SchemaDriftKind::MissingField,
+            field,
+            example: None,
+            count: 1,
+        }))
+        .collect()
+}
+
+/// Строки страницы, разобранные в структуру клиента, вместе с исходным JSON.
+pub struct GuardedRows<T> {
+    pub rows: Vec<(T, Value)>,
+    /// Строк, не подошедших под структуру (записаны как `row_rejected`)
+    pub rejected: usize,
+}
+
+/// Разбирает страницу построчно и записывает расхождения схемы по `endpoint`.
+/// Ошибка записи расхождений только логируется — импорт не прерывается.
+pub async fn guard_rows<T: DeserializeOwned>(
+    endpoint: &str,
+    set: &'static MappingSet,
+    values: Vec<Value>,
+) -> GuardedRows<T> {
+    let mut drifts = detect(set, &values);
+    let mut rejected: BTreeMap<String, (String, i64)> = BTreeMap::new();
+    let mut rows = Vec::with_capacity(values.len());
+    for value in values {
+        match T::deserialize(&value) {
+            Ok(row) => rows.push((row, value)),
+            Err(e) => {
+                let entry = rejected
+                    .entry(e.to_string())
+                    .or_insert_with(|| (example(&value), 0));
+                entry.1 += 1;
+            }
+        }
+    }
+    let rejected_total = rejected.values().map(|(_, count)| *count as usize).sum();
+    drifts.extend(
+        rejected
+            .into_iter()
+            .map(|(field, (example, count))| SchemaDrift {
+                kind: SchemaDriftKind::RowRejected,
+                field,
+                example: Some(example),
+                count,
+            }),
+    );
+
+    if !drifts.is_empty() {
+        if let Err(e) = crate::system::schema_drift::record(endpoint, set.name, &drifts).await {
+            tracing::warn!("schema drift for {}: failed to record: {}", endpoint, e);
+        }
+    }
+
+    GuardedRows {
+        rows,
+        rejected: rejected_total,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::marketplaces::field_mapping::FieldMapping;
+    use serde_json::json;
+
+    static ITEM: MappingSet = MappingSet {
+        name: "test.items",
+        version: 1,
+        fields: &[FieldMapping::new("sku", "sku", FieldKind::Int, 1)],
+        unmapped: &[],
+    };
+
+    static SET: MappingSet = MappingSet {
+        name: "test",
+        version: 1,
+        fields: &[
+            FieldMapping::new("amount", "amount", FieldKind::Float, 1),
+            FieldMapping::new("posting_number", "posting.number", FieldKind::Str, 1),
+            FieldMapping::new("items", "items", FieldKind::List(&ITEM), 1),
+        ],
+        unmapped: &["id"],
+    };
+
+    fn fields(drifts: &[SchemaDrift], kind: SchemaDriftKind) -> Vec<&str> {
+        drifts
+            .iter()
+            .filter(|d| d.kind == kind)
+            .map(|d| d.field.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn known_payload_has_no_drift() {
+        let rows = vec![json!({
+            "id": 1,
+            "amount": null,
+            "posting": { "number": "1-2" },
+            "items": [{ "sku": 5 }]
+        })];
+        assert!(detect(&SET, &rows).is_empty());
+    }
+
+    #[test]
+    fn reports_new_and_missing_fields() {
+        let rows = vec![
+            json!({
+                "id": 1,
+                "posting": { "number": "1-2", "warehouse": "Хоругвино" },
+                "items": [{ "sku": 5, "offer_id": "A-1" }],
+                "bonus": 12.5
+            }),
+            json!({ "id": 2, "posting": { "number": "1-3" }, "items": [] }),
+        ];
+        let drifts = detect(&SET, &rows);
+        assert_eq!(
+            fields(&drifts, SchemaDriftKind::UnknownField),
+            vec!["bonus", "items[].offer_id", "posting.warehouse"]
+        );
+        assert_eq!(
+            fields(&drifts, SchemaDriftKind::MissingField),
+            vec!["amount"]
+        );
+        let bonus = drifts.iter().find(|d| d.field == "bonus").unwrap();
+        assert_eq!(bonus.example.as_deref(), Some("12.5"));
+    }
+
+    #[test]
+    fn empty_page_reports_nothing() {
+        assert!(detect(&SET, &[]).is_empty());
+    }
+
+    #[test]
+    fn long_examples_are_cut() {
+        let text = example(&json!("я".repeat(500)));
+        assert_eq!(text.chars().count(), EXAMPLE_MAX_CHARS + 1);
+    }
+}
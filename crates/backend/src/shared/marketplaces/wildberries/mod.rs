@@ -1,4 +1,4 @@
-use super::{MarketplaceClient, TestConnectionResult};
+use super::{sandbox, MarketplaceClient, TestConnectionResult};
 use async_trait::async_trait;
 use contracts::domain::a006_connection_mp::aggregate::ConnectionMPDto;
 
@@ -47,12 +47,27 @@ impl MarketplaceClient for WildberriesClient {
         }
 
         // Используем ping endpoint для проверки доступа
-        // Wildberries API использует домен seller-analytics-api
-        let url = "https://seller-analytics-api.wildberries.ru/ping";
+        // Wildberries API использует домен seller-analytics-api;
+        // у него нет песочницы, поэтому тестовое подключение проверяем через statistics-api
+        let ping_url = if dto.is_sandbox {
+            "https://statistics-api.wildberries.ru/ping"
+        } else {
+            "https://seller-analytics-api.wildberries.ru/ping"
+        };
+        let url = match sandbox::resolve(dto.is_sandbox, ping_url) {
+            Ok(url) => url,
+            Err(e) => {
+                return TestConnectionResult {
+                    success: false,
+                    message: "Песочница недоступна".into(),
+                    details: Some(format!("{}", e)),
+                }
+            }
+        };
 
         // Выполняем GET запрос
         let response = match client
-            .get(url)
+            .get(&url)
             .header("Authorization", api_key.as_str())
             .send()
             .await
@@ -92,15 +107,21 @@ impl MarketplaceClient for WildberriesClient {
             };
         }
 
+        let limits = rate_limit_info(response.headers());
+
         // Пытаемся получить текст ответа
         match response.text().await {
             Ok(text) => {
                 // Попробуем распарсить как JSON для проверки валидности
                 if serde_json::from_str::<serde_json::Value>(&text).is_ok() {
+                    let details = match limits {
+                        Some(limits) => format!("API ключ валиден; {}", limits),
+                        None => "API ключ валиден".to_string(),
+                    };
                     TestConnectionResult {
                         success: true,
                         message: "Подключение к Wildberries успешно установлено".into(),
-                        details: Some("API ключ валиден".into()),
+                        details: Some(details),
                     }
                 } else {
                     TestConnectionResult {
@@ -118,3 +139,17 @@ impl MarketplaceClient for WildberriesClient {
         }
     }
 }
+
+/// Лимиты запросов из заголовков ответа WB (`X-Ratelimit-*`), если они есть.
+fn rate_limit_info(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    let get = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let remaining = get("x-ratelimit-remaining")?;
+    let mut info = format!("осталось запросов: {}", remaining);
+    if let Some(limit) = get("x-ratelimit-limit") {
+        info.push_str(&format!(" из {}", limit));
+    }
+    if let Some(reset) = get("x-ratelimit-reset") {
+        info.push_str(&format!(", сброс через {} с", reset));
+    }
+    Some(info)
+}
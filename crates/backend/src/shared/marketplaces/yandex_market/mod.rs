@@ -1,4 +1,4 @@
-use super::{MarketplaceClient, TestConnectionResult};
+use super::{sandbox, MarketplaceClient, TestConnectionResult};
 use async_trait::async_trait;
 use contracts::domain::a006_connection_mp::aggregate::{AuthorizationType, ConnectionMPDto};
 use reqwest::RequestBuilder;
@@ -114,9 +114,31 @@ impl YandexMarketClient {
             }
         };
 
+        let resolved = sandbox::resolve(
+            dto.is_sandbox,
+            "https://api.partner.market.yandex.ru/v2/campaigns",
+        )
+        .and_then(|campaigns_url| {
+            sandbox::resolve(
+                dto.is_sandbox,
+                "https://api.partner.market.yandex.ru/v2/auth/token",
+            )
+            .map(|token_url| (campaigns_url, token_url))
+        });
+        let (campaigns_url, token_url) = match resolved {
+            Ok(urls) => urls,
+            Err(e) => {
+                return TestConnectionResult {
+                    success: false,
+                    message: "Песочница недоступна".into(),
+                    details: Some(format!("{}", e)),
+                }
+            }
+        };
+
         let campaigns_request = match apply_yandex_auth(
             client
-                .get("https://api.partner.market.yandex.ru/v2/campaigns")
+                .get(&campaigns_url)
                 .header("Content-Type", "application/json")
                 .query(&[("limit", "100")]),
             dto,
@@ -155,7 +177,7 @@ impl YandexMarketClient {
         if matches!(&dto.authorization_type, AuthorizationType::ApiKey) {
             let token_request = match apply_yandex_auth(
                 client
-                    .post("https://api.partner.market.yandex.ru/v2/auth/token")
+                    .post(&token_url)
                     .header("Content-Type", "application/json"),
                 dto,
             ) {
@@ -248,11 +270,23 @@ impl MarketplaceClient for YandexMarketClient {
 
         // Endpoint для получения информации о кампаниях (простой тестовый запрос)
         // Используем API v2.1
-        let url = "https://api.partner.market.yandex.ru/campaigns";
+        let url = match sandbox::resolve(
+            dto.is_sandbox,
+            "https://api.partner.market.yandex.ru/campaigns",
+        ) {
+            Ok(url) => url,
+            Err(e) => {
+                return TestConnectionResult {
+                    success: false,
+                    message: "Песочница недоступна".into(),
+                    details: Some(format!("{}", e)),
+                }
+            }
+        };
 
         // Выполняем запрос
         let mut request_builder = match apply_yandex_auth(
-            client.get(url).header("Content-Type", "application/json"),
+            client.get(&url).header("Content-Type", "application/json"),
             dto,
         ) {
             Ok(request) => request,
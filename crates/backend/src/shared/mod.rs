@@ -1,14 +1,23 @@
 pub mod analytics;
 pub mod bi_timeline;
+pub mod calc_column;
 pub mod change_token;
 pub mod config;
 pub mod data;
 pub mod data_access;
+pub mod delta;
 pub mod drilldown;
+pub mod error;
+pub mod export;
 pub mod format;
 pub mod llm;
 pub mod logger;
 pub mod mail;
 pub mod marketplaces;
+pub mod ndjson;
+pub mod optimistic_lock;
+pub mod quick_filter;
 pub mod representation;
+pub mod secrets;
+pub mod telegram;
 pub mod universal_dashboard;
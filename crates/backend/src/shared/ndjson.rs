@@ -0,0 +1,137 @@
+//! Построчное чтение NDJSON из потока кусков тела запроса.
+//!
+//! Куски приходят произвольной длины, строка может быть разрезана между ними.
+//! Сжатый поток (gzip) распознаётся по сигнатуре `1f 8b` в начале тела или по
+//! явному флагу и распаковывается на лету — весь файл в памяти не держится.
+
+use std::io::Write;
+
+use anyhow::{anyhow, Result};
+use flate2::write::GzDecoder;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Default)]
+pub struct NdjsonLines {
+    /// Распакованные байты без завершённой строки в конце.
+    pending: Vec<u8>,
+    /// Начало тела до определения формата (нужны первые 2 байта).
+    head: Vec<u8>,
+    gzip: Option<GzDecoder<Vec<u8>>>,
+    force_gzip: bool,
+    started: bool,
+}
+
+impl NdjsonLines {
+    /// `gzip` — тело заведомо сжато (`Content-Encoding: gzip`).
+    pub fn new(gzip: bool) -> Self {
+        Self {
+            force_gzip: gzip,
+            ..Self::default()
+        }
+    }
+
+    /// Принять очередной кусок тела и вернуть завершённые строки (пустые пропускаются).
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Vec<String>> {
+        if !self.started {
+            self.head.extend_from_slice(chunk);
+            if self.head.len() < GZIP_MAGIC.len() && !self.force_gzip {
+                return Ok(Vec::new());
+            }
+            self.started = true;
+            if self.force_gzip || self.head.starts_with(&GZIP_MAGIC) {
+                self.gzip = Some(GzDecoder::new(Vec::new()));
+            }
+            let head = std::mem::take(&mut self.head);
+            return self.push(&head);
+        }
+        self.push(chunk)
+    }
+
+    /// Конец тела: дописать хвост без завершающего перевода строки.
+    pub fn finish(mut self) -> Result<Vec<String>> {
+        if !self.started {
+            let head = std::mem::take(&mut self.head);
+            self.started = true;
+            self.push(&head)?;
+        }
+        if let Some(mut decoder) = self.gzip.take() {
+            decoder
+                .try_finish()
+                .map_err(|e| anyhow!("Повреждённый gzip: {}", e))?;
+            self.pending.append(decoder.get_mut());
+        }
+        let mut lines = self.drain_lines()?;
+        let tail = std::mem::take(&mut self.pending);
+        if let Some(line) = to_line(tail)? {
+            lines.push(line);
+        }
+        Ok(lines)
+    }
+
+    fn push(&mut self, bytes: &[u8]) -> Result<Vec<String>> {
+        match self.gzip.as_mut() {
+            Some(decoder) => {
+                decoder
+                    .write_all(bytes)
+                    .map_err(|e| anyhow!("Повреждённый gzip: {}", e))?;
+                self.pending.append(decoder.get_mut());
+            }
+            None => self.pending.extend_from_slice(bytes),
+        }
+        self.drain_lines()
+    }
+
+    fn drain_lines(&mut self) -> Result<Vec<String>> {
+        let Some(last_newline) = self.pending.iter().rposition(|b| *b == b'\n') else {
+            return Ok(Vec::new());
+        };
+        let rest = self.pending.split_off(last_newline + 1);
+        let complete = std::mem::replace(&mut self.pending, rest);
+        let mut lines = Vec::new();
+        for raw in complete.split(|b| *b == b'\n') {
+            if let Some(line) = to_line(raw.to_vec())? {
+                lines.push(line);
+            }
+        }
+        Ok(lines)
+    }
+}
+
+fn to_line(raw: Vec<u8>) -> Result<Option<String>> {
+    let line = String::from_utf8(raw).map_err(|_| anyhow!("Строка не в UTF-8"))?;
+    let line = line.trim();
+    Ok((!line.is_empty()).then(|| line.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+
+    fn read_all(body: &[u8], chunk: usize, gzip: bool) -> Vec<String> {
+        let mut reader = NdjsonLines::new(gzip);
+        let mut lines = Vec::new();
+        for part in body.chunks(chunk) {
+            lines.extend(reader.feed(part).unwrap());
+        }
+        lines.extend(reader.finish().unwrap());
+        lines
+    }
+
+    #[test]
+    fn splits_lines_across_chunks_plain_and_gzip() {
+        let body = b"{\"a\":1}\r\n\n{\"a\":2}\n{\"a\":3}";
+        let expected = vec!["{\"a\":1}", "{\"a\":2}", "{\"a\":3}"];
+        assert_eq!(read_all(body, 1, false), expected);
+        assert_eq!(read_all(body, 5, false), expected);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body).unwrap();
+        let gz = encoder.finish().unwrap();
+        assert_eq!(read_all(&gz, 3, false), expected);
+        assert_eq!(read_all(&gz, 64, true), expected);
+
+        assert!(read_all(b"", 1, false).is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+//! Optimistic locking для команд над документами (проведение, отмена проведения).
+//!
+//! Клиент передаёт `?version=N` — версию документа, которую он показывает.
+//! Команда занимает эту версию атомарно в своей транзакции ([`claim_version`]):
+//! `UPDATE ... SET version = version + 1 WHERE id = ? AND version = N`. Если строку
+//! уже изменил другой запрос, обновится 0 строк — транзакция откатывается, а клиент
+//! получает 409 с [`VersionConflict`] в `details` и предлагает перезагрузить документ.
+//! Без параметра проверка пропускается (пакетные операции, регламентные задачи).
+
+use anyhow::Result;
+use contracts::domain::common::VersionConflict;
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+use serde::Deserialize;
+
+use crate::shared::error::AppError;
+
+/// Query-параметр с версией документа, загруженной клиентом
+#[derive(Debug, Default, Deserialize)]
+pub struct ExpectedVersion {
+    pub version: Option<i32>,
+}
+
+/// Версия документа устарела, команда не выполнена (в ответе — 409).
+#[derive(Debug)]
+pub struct StaleVersion(pub VersionConflict);
+
+impl std::fmt::Display for StaleVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Stale document version: expected {}, current {}",
+            self.0.expected_version, self.0.current_version
+        )
+    }
+}
+
+impl std::error::Error for StaleVersion {}
+
+/// Занимает версию `expected` документа `id` таблицы `table` в транзакции команды.
+/// Версия в БД увеличивается на 1; запись документа в той же транзакции должна
+/// сохранить то же значение (`expected + 1`).
+pub async fn claim_version<C: ConnectionTrait>(
+    db: &C,
+    table: &str,
+    id: &str,
+    expected: i32,
+) -> Result<()> {
+    // Документ из архивного месяца сначала возвращается в рабочую таблицу
+    crate::shared::data::projection_archive::restore_with_conn(db, table, id).await?;
+    let updated = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            format!(
+                "UPDATE {} SET version = version + 1 WHERE id = ? AND version = ?",
+                table
+            ),
+            [id.into(), expected.into()],
+        ))
+        .await?
+        .rows_affected();
+    if updated > 0 {
+        return Ok(());
+    }
+
+    let current = db
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            format!("SELECT version FROM {} WHERE id = ?", table),
+            [id.into()],
+        ))
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Document not found: {}", id))?
+        .try_get::<i32>("", "version")?;
+    Err(StaleVersion(VersionConflict {
+        expected_version: expected,
+        current_version: current,
+    })
+    .into())
+}
+
+/// Ошибка команды с версией: [`StaleVersion`] — 409, остальное — 500 с контекстом.
+pub fn command_error(err: anyhow::Error, context: &'static str) -> AppError {
+    match err.downcast::<StaleVersion>() {
+        Ok(StaleVersion(conflict)) => {
+            AppError::conflict("Документ изменён другим пользователем", conflict)
+        }
+        Err(err) => AppError::Internal(err.context(context)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use sea_orm::{ConnectOptions, Database, TransactionTrait};
+
+    async fn document_table() -> sea_orm::DatabaseConnection {
+        let mut options = ConnectOptions::new("sqlite::memory:");
+        options.max_connections(1);
+        let db = Database::connect(options).await.unwrap();
+        db.execute_unprepared("CREATE TABLE doc (id TEXT PRIMARY KEY, version INTEGER NOT NULL)")
+            .await
+            .unwrap();
+        db.execute_unprepared("INSERT INTO doc VALUES ('d1', 3)")
+            .await
+            .unwrap();
+        db
+    }
+
+    async fn version(db: &sea_orm::DatabaseConnection) -> i32 {
+        db.query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT version FROM doc WHERE id = 'd1'",
+        ))
+        .await
+        .unwrap()
+        .unwrap()
+        .try_get::<i32>("", "version")
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn second_command_with_same_version_conflicts() {
+        let db = document_table().await;
+
+        let txn = db.begin().await.unwrap();
+        claim_version(&txn, "doc", "d1", 3).await.unwrap();
+        txn.commit().await.unwrap();
+        assert_eq!(version(&db).await, 4);
+
+        let txn = db.begin().await.unwrap();
+        let err = claim_version(&txn, "doc", "d1", 3).await.unwrap_err();
+        drop(txn);
+        let err = command_error(err, "Failed to post document");
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+        match err {
+            AppError::Conflict { details, .. } => assert_eq!(
+                details,
+                Some(serde_json::json!({"expected_version": 3, "current_version": 4}))
+            ),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(version(&db).await, 4);
+    }
+
+    #[tokio::test]
+    async fn missing_document_is_internal_error() {
+        let db = document_table().await;
+        let err = claim_version(&db, "doc", "nope", 1).await.unwrap_err();
+        assert_eq!(
+            command_error(err, "Failed to post document").status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
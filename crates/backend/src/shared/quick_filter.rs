@@ -0,0 +1,212 @@
+//! Перевод строки быстрого фильтра (`contracts::shared::quick_filter`) в условия WHERE.
+//!
+//! Список описывает, каким SQL-выражением представлено каждое поле; разбор и проверка
+//! формата значений — общие с фронтендом. Значения подставляются в SQL так же, как в
+//! остальных list-запросах: текст экранируется, числа и даты проходят проверку формата.
+
+use contracts::shared::quick_filter::{
+    parse_for, QuickFilterField, QuickFilterKind, QuickFilterOp, QuickFilterTerm,
+};
+
+/// SQL-представление поля быстрого фильтра.
+pub enum QuickFilterColumn {
+    /// Выражение колонки (текст, число или дата по виду поля).
+    Expr(&'static str),
+    /// Перечисление: значение → готовое условие.
+    Cases(&'static [(&'static str, &'static str)]),
+}
+
+/// Результат разбора: условия по полям и слова свободного поиска (через пробел).
+#[derive(Debug, Default)]
+pub struct QuickFilterWhere {
+    pub conditions: Vec<String>,
+    pub free_text: Option<String>,
+}
+
+fn sql_text(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn comparison(op: QuickFilterOp) -> &'static str {
+    match op {
+        QuickFilterOp::Match | QuickFilterOp::Eq => "=",
+        QuickFilterOp::Ne => "<>",
+        QuickFilterOp::Gt => ">",
+        QuickFilterOp::Ge => ">=",
+        QuickFilterOp::Lt => "<",
+        QuickFilterOp::Le => "<=",
+    }
+}
+
+fn term_condition(
+    field: &QuickFilterField,
+    column: &QuickFilterColumn,
+    term: &QuickFilterTerm,
+) -> Result<String, String> {
+    let value = term.value.as_str();
+    match (field.kind, column) {
+        (QuickFilterKind::Enum(_), QuickFilterColumn::Cases(cases)) => {
+            let cond = cases
+                .iter()
+                .find(|(v, _)| v.eq_ignore_ascii_case(value))
+                .map(|(_, cond)| *cond)
+                .ok_or_else(|| format!("Поле «{}»: нет условия для «{}»", field.key, value))?;
+            Ok(if term.op == QuickFilterOp::Ne {
+                format!("NOT ({})", cond)
+            } else {
+                format!("({})", cond)
+            })
+        }
+        (QuickFilterKind::Text, QuickFilterColumn::Expr(expr)) => Ok(match term.op {
+            QuickFilterOp::Match => format!(
+                "COALESCE({}, '') LIKE '%{}%'",
+                expr,
+                value.replace('\'', "''")
+            ),
+            QuickFilterOp::Ne => format!("COALESCE({}, '') <> {}", expr, sql_text(value)),
+            _ => format!("{} = {}", expr, sql_text(value)),
+        }),
+        (QuickFilterKind::Number, QuickFilterColumn::Expr(expr)) => {
+            let number: f64 = value
+                .replace(',', ".")
+                .parse()
+                .map_err(|_| format!("Поле «{}» ожидает число", field.key))?;
+            Ok(format!("{} {} {}", expr, comparison(term.op), number))
+        }
+        (QuickFilterKind::Date, QuickFilterColumn::Expr(expr)) => Ok(format!(
+            "substr({}, 1, {}) {} {}",
+            expr,
+            value.len(),
+            comparison(term.op),
+            sql_text(value)
+        )),
+        _ => Err(format!(
+            "Поле «{}» не поддерживается этим списком",
+            field.key
+        )),
+    }
+}
+
+/// Разбирает строку `input` по полям списка и строит условия WHERE.
+/// Ошибка — текст для пользователя (неизвестное поле, неверный формат значения,
+/// финансовое поле без доступа к финансам — `show_finance == false`).
+pub fn build_where(
+    input: &str,
+    fields: &[QuickFilterField],
+    columns: &[(&str, QuickFilterColumn)],
+    show_finance: bool,
+) -> Result<QuickFilterWhere, String> {
+    let query = parse_for(fields, input)?;
+    let mut conditions = Vec::with_capacity(query.terms.len());
+    for term in &query.terms {
+        let key = term.field.as_deref().unwrap_or_default();
+        let field = fields.iter().find(|f| f.key == key);
+        let column = columns.iter().find(|(k, _)| *k == key).map(|(_, c)| c);
+        match (field, column) {
+            (Some(field), _) if field.finance && !show_finance => {
+                return Err(format!("Поле «{}» недоступно без доступа к финансам", key));
+            }
+            (Some(field), Some(column)) => conditions.push(term_condition(field, column, term)?),
+            _ => return Err(format!("Поле «{}» не поддерживается этим списком", key)),
+        }
+    }
+    let free_text = query.free_text.join(" ");
+    Ok(QuickFilterWhere {
+        conditions,
+        free_text: (!free_text.is_empty()).then_some(free_text),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIELDS: &[QuickFilterField] = &[
+        QuickFilterField {
+            key: "status",
+            label: "Проведение",
+            kind: QuickFilterKind::Enum(&["posted", "unposted"]),
+            finance: false,
+        },
+        QuickFilterField {
+            key: "warehouse",
+            label: "Склад",
+            kind: QuickFilterKind::Text,
+            finance: false,
+        },
+        QuickFilterField {
+            key: "amount",
+            label: "Сумма",
+            kind: QuickFilterKind::Number,
+            finance: true,
+        },
+    ];
+
+    const COLUMNS: &[(&str, QuickFilterColumn)] = &[
+        (
+            "status",
+            QuickFilterColumn::Cases(&[
+                ("posted", "s.is_posted = 1"),
+                ("unposted", "s.is_posted = 0"),
+            ]),
+        ),
+        ("warehouse", QuickFilterColumn::Expr("s.warehouse")),
+        ("amount", QuickFilterColumn::Expr("s.amount")),
+    ];
+
+    #[test]
+    fn builds_conditions_and_free_text() {
+        let w = build_where(
+            r#"status:unposted warehouse:"O'Hara" amount>1000 ТВИЗ"#,
+            FIELDS,
+            COLUMNS,
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            w.conditions,
+            vec![
+                "(s.is_posted = 0)".to_string(),
+                "COALESCE(s.warehouse, '') LIKE '%O''Hara%'".to_string(),
+                "s.amount > 1000".to_string(),
+            ]
+        );
+        assert_eq!(w.free_text.as_deref(), Some("ТВИЗ"));
+    }
+
+    #[test]
+    fn list_columns_cover_contract_fields() {
+        let lists: &[(&[QuickFilterField], &[(&str, QuickFilterColumn)])] = &[
+            (
+                contracts::domain::a012_wb_sales::quick_filter::QUICK_FILTER_FIELDS,
+                crate::domain::a012_wb_sales::repository::QUICK_FILTER_COLUMNS,
+            ),
+            (
+                contracts::domain::a015_wb_orders::quick_filter::QUICK_FILTER_FIELDS,
+                crate::domain::a015_wb_orders::repository::QUICK_FILTER_COLUMNS,
+            ),
+        ];
+        for (fields, columns) in lists {
+            for field in *fields {
+                assert!(
+                    columns.iter().any(|(k, _)| *k == field.key),
+                    "no column for quick filter field {}",
+                    field.key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_fields_without_column() {
+        assert!(build_where("amount>1", FIELDS, &COLUMNS[..2], true).is_err());
+    }
+
+    #[test]
+    fn rejects_finance_fields_without_finance_access() {
+        let error =
+            build_where("warehouse:Коледино amount>1000", FIELDS, COLUMNS, false).unwrap_err();
+        assert!(error.contains("amount"));
+        assert!(build_where("status:posted warehouse:Коледино", FIELDS, COLUMNS, false).is_ok());
+    }
+}
@@ -0,0 +1,145 @@
+//! Шифрование секретов, хранимых в БД (API-ключи подключений a006).
+//!
+//! Формат зашифрованного значения: `enc:v1:<base64(nonce || ciphertext || tag)>`,
+//! алгоритм — AES-256-GCM, ключ — SHA-256 от `[secrets].encryption_key`.
+//! Значения без префикса считаются открытым текстом (данные до включения
+//! шифрования) и читаются как есть.
+
+use std::sync::OnceLock;
+
+use anyhow::anyhow;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use sha2::{Digest, Sha256};
+
+const PREFIX: &str = "enc:v1:";
+
+/// Маска, которой API отдаёт секреты наружу: `••••` + последние 4 символа.
+pub const MASK: &str = "••••";
+
+static KEY: OnceLock<Option<LessSafeKey>> = OnceLock::new();
+
+/// Установить ключ шифрования (один раз при старте). Пустая строка — шифрование выключено.
+pub fn set_encryption_key(secret: &str) {
+    let _ = KEY.set(derive_key(secret));
+}
+
+/// Шифрование включено (ключ задан в config.toml).
+pub fn is_enabled() -> bool {
+    key().is_some()
+}
+
+fn key() -> Option<&'static LessSafeKey> {
+    KEY.get().and_then(|k| k.as_ref())
+}
+
+fn derive_key(secret: &str) -> Option<LessSafeKey> {
+    let secret = secret.trim();
+    if secret.is_empty() {
+        return None;
+    }
+    let digest = Sha256::digest(secret.as_bytes());
+    let unbound = UnboundKey::new(&AES_256_GCM, &digest).ok()?;
+    Some(LessSafeKey::new(unbound))
+}
+
+/// Значение уже зашифровано.
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(PREFIX)
+}
+
+/// Зашифровать значение для записи в БД. Без ключа и для пустых/уже
+/// зашифрованных значений возвращает вход без изменений.
+pub fn encrypt(plain: &str) -> anyhow::Result<String> {
+    match key() {
+        Some(key) if !plain.is_empty() && !is_encrypted(plain) => encrypt_with(key, plain),
+        _ => Ok(plain.to_string()),
+    }
+}
+
+/// Расшифровать значение из БД. Открытый текст возвращается как есть.
+pub fn decrypt(stored: &str) -> anyhow::Result<String> {
+    if !is_encrypted(stored) {
+        return Ok(stored.to_string());
+    }
+    let key = key().ok_or_else(|| anyhow!("[secrets].encryption_key не задан"))?;
+    decrypt_with(key, stored)
+}
+
+fn encrypt_with(key: &LessSafeKey, plain: &str) -> anyhow::Result<String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| anyhow!("Не удалось сгенерировать nonce"))?;
+    let mut buf = plain.as_bytes().to_vec();
+    key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut buf)
+        .map_err(|_| anyhow!("Ошибка шифрования"))?;
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&buf);
+    Ok(format!("{}{}", PREFIX, STANDARD.encode(out)))
+}
+
+fn decrypt_with(key: &LessSafeKey, stored: &str) -> anyhow::Result<String> {
+    let raw = STANDARD
+        .decode(&stored[PREFIX.len()..])
+        .map_err(|e| anyhow!("Некорректный base64 секрета: {}", e))?;
+    if raw.len() < NONCE_LEN {
+        return Err(anyhow!("Зашифрованное значение слишком короткое"));
+    }
+    let (nonce, cipher) = raw.split_at(NONCE_LEN);
+    let nonce =
+        Nonce::try_assume_unique_for_key(nonce).map_err(|_| anyhow!("Некорректный nonce"))?;
+    let mut buf = cipher.to_vec();
+    let plain = key
+        .open_in_place(nonce, Aad::empty(), &mut buf)
+        .map_err(|_| anyhow!("Не удалось расшифровать секрет (неверный ключ?)"))?;
+    Ok(String::from_utf8(plain.to_vec())?)
+}
+
+/// Маскированное представление секрета для ответа API.
+pub fn mask(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.is_empty() {
+        return String::new();
+    }
+    if chars.len() <= 8 {
+        return MASK.to_string();
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}{}", MASK, tail)
+}
+
+/// Значение пришло с клиента в маскированном виде (пользователь токен не менял).
+pub fn is_masked(value: &str) -> bool {
+    value.starts_with(MASK)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_and_plaintext_fallback() {
+        let key = derive_key("test-secret").unwrap();
+        let enc = encrypt_with(&key, "wb-token-123").unwrap();
+        assert!(is_encrypted(&enc));
+        assert!(!enc.contains("wb-token-123"));
+        assert_eq!(decrypt_with(&key, &enc).unwrap(), "wb-token-123");
+
+        let other = derive_key("other-secret").unwrap();
+        assert!(decrypt_with(&other, &enc).is_err());
+        assert!(derive_key("  ").is_none());
+        assert_eq!(decrypt("legacy-plain").unwrap(), "legacy-plain");
+    }
+
+    #[test]
+    fn mask_hides_all_but_tail() {
+        assert_eq!(mask(""), "");
+        assert_eq!(mask("short"), MASK);
+        let masked = mask("eyJhbGciOiJFUzI1NiJ9.abcd");
+        assert_eq!(masked, format!("{}abcd", MASK));
+        assert!(is_masked(&masked));
+        assert!(!is_masked("eyJhbGciOiJFUzI1NiJ9.abcd"));
+    }
+}
@@ -0,0 +1,40 @@
+//! Отправка сообщений через Telegram Bot API (канал уведомлений «Telegram»).
+//!
+//! Бот настраивается в `config.toml` секции `[telegram]`; пользователь указывает
+//! свой chat id в настройках уведомлений после того, как написал боту `/start`.
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::json;
+
+use crate::shared::config::get_telegram_config;
+
+/// Лимит длины сообщения Telegram
+const MAX_MESSAGE_CHARS: usize = 4096;
+
+pub async fn send_message(chat_id: &str, text: &str) -> Result<()> {
+    let cfg = get_telegram_config();
+    if !cfg.is_ready() {
+        return Err(anyhow!("Telegram is disabled in config.toml"));
+    }
+    let text: String = text.chars().take(MAX_MESSAGE_CHARS).collect();
+    let url = format!(
+        "https://api.telegram.org/bot{}/sendMessage",
+        cfg.bot_token.trim()
+    );
+    let response = reqwest::Client::new()
+        .post(&url)
+        .json(&json!({
+            "chat_id": chat_id,
+            "text": text,
+            "disable_web_page_preview": true,
+        }))
+        .send()
+        .await
+        .context("Failed to call Telegram API")?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Telegram API returned {}: {}", status, body));
+    }
+    Ok(())
+}
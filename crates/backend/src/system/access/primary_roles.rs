@@ -65,12 +65,17 @@ pub const MANAGER_GRANTS: &[(&str, &str)] = &[
     ("u506_import_from_lemanapro", "all"),
     ("u507_import_from_erp", "all"),
     ("u508_repost_documents", "all"),
+    ("u509_link_ozon_skus", "all"),
     // System views
     ("general_ledger", "all"),
     ("data_view", "all"),
     ("bi_timeline", "all"),
     ("dashboard", "all"),
     ("knowledge_base", "all"),
+    // Field-level
+    ("view_finance", "read"),
+    // Actions
+    ("approve_return_disposition", "all"),
 ];
 
 /// Default scope grants for the `operator` role.
@@ -128,6 +133,8 @@ pub const OPERATOR_GRANTS: &[(&str, &str)] = &[
     ("p908_wb_goods_prices", "read"),
     ("p912_nomenclature_costs", "read"),
     ("bi_timeline", "read"),
+    // Field-level
+    ("view_finance", "read"),
 ];
 
 /// Default scope grants for the `viewer` role.
@@ -156,6 +163,32 @@ pub const VIEWER_GRANTS: &[(&str, &str)] = &[
     ("p900_mp_sales_register", "read"),
     ("p904_sales_data", "read"),
     ("p912_nomenclature_costs", "read"),
+    // Field-level
+    ("view_finance", "read"),
+];
+
+/// Default scope grants for the `warehouse` role.
+/// Warehouse staff work with documents and supplies but do not see amounts:
+/// no `view_finance`, so finance columns are masked in document lists and cards.
+pub const WAREHOUSE_GRANTS: &[(&str, &str)] = &[
+    // Aggregates — references: read only
+    ("a002_organization", "read"),
+    ("a004_nomenclature", "read"),
+    ("a005_marketplace", "read"),
+    ("a007_marketplace_product", "read"),
+    // Aggregates — marketplace documents: read only
+    ("a009_ozon_returns", "read"),
+    ("a010_ozon_fbs_posting", "read"),
+    ("a011_ozon_fbo_posting", "read"),
+    ("a012_wb_sales", "read"),
+    ("a013_ym_order", "read"),
+    ("a015_wb_orders", "read"),
+    ("a016_ym_returns", "read"),
+    // Aggregates — warehouse operations: full access
+    ("a029_wb_supply", "all"),
+    ("a032_wb_returns_claims", "all"),
+    // Projections
+    ("p901_nomenclature_barcodes", "read"),
 ];
 
 /// `admin` primary role: all access is granted via `is_admin=true` bypass.
@@ -170,13 +203,14 @@ pub fn grants_for_role(role_code: &str) -> &'static [(&'static str, &'static str
         "manager" => MANAGER_GRANTS,
         "operator" => OPERATOR_GRANTS,
         "viewer" => VIEWER_GRANTS,
+        "warehouse" => WAREHOUSE_GRANTS,
         _ => &[],
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::OPERATOR_GRANTS;
+    use super::{grants_for_role, OPERATOR_GRANTS, WAREHOUSE_GRANTS};
 
     #[test]
     fn operator_can_use_llm_chat_without_administering_agents() {
@@ -186,4 +220,17 @@ mod tests {
         // Артефакты чата должны быть доступны, иначе карточки артефактов отдают 403.
         assert!(OPERATOR_GRANTS.contains(&("a019_llm_artifact", "all")));
     }
+
+    #[test]
+    fn finance_hidden_only_from_warehouse_role() {
+        assert!(!WAREHOUSE_GRANTS
+            .iter()
+            .any(|(scope, _)| *scope == "view_finance"));
+        for role in ["manager", "operator", "viewer"] {
+            assert!(
+                grants_for_role(role).contains(&("view_finance", "read")),
+                "{role} must keep finance columns"
+            );
+        }
+    }
 }
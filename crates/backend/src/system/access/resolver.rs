@@ -8,6 +8,7 @@
 
 use anyhow::Result;
 use contracts::shared::access::ScopeAccess;
+use contracts::system::auth::TokenClaims;
 use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
 
 use super::primary_roles;
@@ -69,15 +70,7 @@ pub async fn resolve_user_scopes(user_id: &str) -> Result<Vec<ScopeAccess>> {
         return Ok(vec![]);
     }
 
-    // Collect grants: scope_id → max mode ("all" > "read")
-    let mut grants: std::collections::HashMap<String, String> = std::collections::HashMap::new();
-
-    // 1. Apply primary role grants from code catalog
-    for (scope_id, mode) in primary_roles::grants_for_role(&primary_role_code) {
-        merge_grant(&mut grants, scope_id, mode);
-    }
-
-    // 2. Apply additional DB role grants
+    // Additional DB role grants
     let db_rows = conn
         .query_all(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
@@ -89,10 +82,30 @@ pub async fn resolve_user_scopes(user_id: &str) -> Result<Vec<ScopeAccess>> {
         ))
         .await?;
 
+    let mut additional = Vec::new();
     for row in db_rows {
         let scope: String = row.try_get("", "access_scope_id")?;
         let mode: String = row.try_get("", "access_mode")?;
-        merge_grant(&mut grants, &scope, &mode);
+        additional.push((scope, mode));
+    }
+
+    Ok(merge_scopes(&primary_role_code, &additional))
+}
+
+/// Effective scopes for a primary role plus additional `(scope_id, mode)` grants,
+/// sorted by scope_id. Shared by the resolver and the admin role preview.
+pub fn merge_scopes(primary_role_code: &str, additional: &[(String, String)]) -> Vec<ScopeAccess> {
+    // Collect grants: scope_id → max mode ("all" > "read")
+    let mut grants: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+
+    // 1. Apply primary role grants from code catalog
+    for (scope_id, mode) in primary_roles::grants_for_role(primary_role_code) {
+        merge_grant(&mut grants, scope_id, mode);
+    }
+
+    // 2. Apply additional DB role grants
+    for (scope_id, mode) in additional {
+        merge_grant(&mut grants, scope_id, mode);
     }
 
     // Convert to sorted Vec<ScopeAccess>
@@ -101,8 +114,25 @@ pub async fn resolve_user_scopes(user_id: &str) -> Result<Vec<ScopeAccess>> {
         .map(|(scope_id, mode)| ScopeAccess { scope_id, mode })
         .collect();
     result.sort_by(|a, b| a.scope_id.cmp(&b.scope_id));
+    result
+}
 
-    Ok(result)
+/// Whether the user may see finance fields of document DTOs (see `FinanceFields`).
+pub async fn can_view_finance(claims: &TokenClaims) -> Result<bool> {
+    if claims.is_admin {
+        return Ok(true);
+    }
+    let scopes = resolve_user_scopes(&claims.sub).await?;
+    Ok(contracts::shared::access::can_view_finance(false, &scopes))
+}
+
+/// Whether the user has at least read access to the scope (admins always do).
+pub async fn can_read_scope(claims: &TokenClaims, scope_id: &str) -> Result<bool> {
+    if claims.is_admin {
+        return Ok(true);
+    }
+    let scopes = resolve_user_scopes(&claims.sub).await?;
+    Ok(scopes.iter().any(|s| s.scope_id == scope_id))
 }
 
 /// Get the primary_role_code for a user (used during token generation).
@@ -138,3 +168,30 @@ fn merge_grant(grants: &mut std::collections::HashMap<String, String>, scope_id:
         *entry = "all".to_string();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::merge_scopes;
+
+    #[test]
+    fn additional_grants_upgrade_but_never_downgrade_primary_role() {
+        let scopes = merge_scopes(
+            "viewer",
+            &[
+                ("a012_wb_sales".to_string(), "all".to_string()),
+                ("view_finance".to_string(), "read".to_string()),
+                ("custom_scope".to_string(), "read".to_string()),
+            ],
+        );
+        let mode = |scope: &str| {
+            scopes
+                .iter()
+                .find(|s| s.scope_id == scope)
+                .map(|s| s.mode.as_str())
+        };
+        assert_eq!(mode("a012_wb_sales"), Some("all"));
+        assert_eq!(mode("view_finance"), Some("read"));
+        assert_eq!(mode("custom_scope"), Some("read"));
+        assert!(scopes.windows(2).all(|w| w[0].scope_id < w[1].scope_id));
+    }
+}
@@ -23,6 +23,18 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: None,
         mode: PolicyMode::Public,
     },
+    RoutePolicy {
+        method: "GET",
+        path: "/healthz",
+        scope_id: None,
+        mode: PolicyMode::Public,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/readyz",
+        scope_id: None,
+        mode: PolicyMode::Public,
+    },
     RoutePolicy {
         method: "POST",
         path: "/api/system/auth/login",
@@ -41,12 +53,265 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: None,
         mode: PolicyMode::Public,
     },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/auth/oidc",
+        scope_id: None,
+        mode: PolicyMode::Public,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/auth/oidc/start",
+        scope_id: None,
+        mode: PolicyMode::Public,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/auth/oidc/callback",
+        scope_id: None,
+        mode: PolicyMode::Public,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/system/auth/oidc/exchange",
+        scope_id: None,
+        mode: PolicyMode::Public,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/branding",
+        scope_id: None,
+        mode: PolicyMode::Public,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/environment",
+        scope_id: None,
+        mode: PolicyMode::Public,
+    },
+    // External BI API: no user session, guarded by check_api_key.
+    RoutePolicy {
+        method: "GET",
+        path: "/api/ext/v1/changes",
+        scope_id: None,
+        mode: PolicyMode::Public,
+    },
     RoutePolicy {
         method: "GET",
         path: "/api/system/auth/me",
         scope_id: None,
         mode: PolicyMode::AuthOnly,
     },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/notifications",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/system/notifications/read",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/system/notifications/preferences",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/announcements",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/system/announcements/read",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/data-freshness",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/document-audit/:entity/:id",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/print-templates",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/print-templates/:id/render/:document_id",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/exports",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/exports/:id/download",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "DELETE",
+        path: "/api/system/exports/:id",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/export-profiles",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/system/export-profiles",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "PUT",
+        path: "/api/system/export-profiles/:id",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "DELETE",
+        path: "/api/system/export-profiles/:id",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/system/export-profiles/:id/run",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/active-organization",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "PUT",
+        path: "/api/system/active-organization",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/operations",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/operations/:id",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/scheduled-posts",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "DELETE",
+        path: "/api/scheduled-posts/:id",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/external-refs",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/external-refs/search",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/external-refs/:id",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/return-dispositions",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/return-dispositions/photos",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/return-dispositions/photos/:file_id",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/return-dispositions/:id/submit",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/return-dispositions/:id/approve",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/return-dispositions/:id/reject",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/presence/stream",
+        scope_id: None,
+        mode: PolicyMode::Public,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/system/presence",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/system/presence/saved",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
     // ========================================================================
     // System admin routes
     // ========================================================================
@@ -56,12 +321,30 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: None,
         mode: PolicyMode::AdminOnly,
     },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/users/expiring",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/system/users/:id",
         scope_id: None,
         mode: PolicyMode::AdminOnly,
     },
+    RoutePolicy {
+        method: "*",
+        path: "/api/system/users/:id/roles",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/system/users/:id/organizations",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/system/users/:id/change-password",
@@ -80,6 +363,12 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: None,
         mode: PolicyMode::AdminOnly,
     },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/roles/:id/preview",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/system/roles/:id/permissions",
@@ -87,68 +376,284 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         mode: PolicyMode::AdminOnly,
     },
     RoutePolicy {
-        method: "GET",
-        path: "/api/system/scopes",
+        method: "GET",
+        path: "/api/system/scopes",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/system/runtime-info",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/sys/tasks",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/sys/tasks/:id",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/sys/tasks/import-summary/subscription",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/sys/tasks/:id/toggle_enabled",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/tasks/schedule-preview",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/tasks/runs/active/progress",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/tasks/runs/active/slots",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/tasks/:id/progress/:session_id",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/tasks/:id/log/:session_id",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/ext-api/history",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/ext-api/summary",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/ext-api/recent",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/bulk-operations",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/sys/bulk-operations/undo-window",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/sys/bulk-operations/:id/undo",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/sys/bulk-operations/lookup",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/sys/bulk-operations/reassign",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/sys/bulk-import/p903",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/projection-archive/status",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/sys/projection-archive/move",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/import-provenance",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/projection-snapshots",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/sys/projection-snapshots",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/projection-snapshots/compare",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "DELETE",
+        path: "/api/sys/projection-snapshots/:id",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/sys/branding",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "DELETE",
+        path: "/api/sys/branding/:organization_id",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/sys/announcements",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/sys/announcements/:id",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/sys/data-freshness/sla",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/quotas",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "PUT",
+        path: "/api/sys/quotas/:organization_id",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/sys/environment/confirm",
+        scope_id: None,
+        mode: PolicyMode::AuthOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/description-templates",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
+    RoutePolicy {
+        method: "PUT",
+        path: "/api/sys/description-templates/:entity_type",
         scope_id: None,
         mode: PolicyMode::AdminOnly,
     },
     RoutePolicy {
-        method: "GET",
-        path: "/api/system/runtime-info",
+        method: "DELETE",
+        path: "/api/sys/description-templates/:entity_type",
         scope_id: None,
-        mode: PolicyMode::AuthOnly,
+        mode: PolicyMode::AdminOnly,
     },
     RoutePolicy {
-        method: "*",
-        path: "/api/sys/tasks",
+        method: "POST",
+        path: "/api/sys/description-templates/:entity_type/apply",
         scope_id: None,
         mode: PolicyMode::AdminOnly,
     },
     RoutePolicy {
-        method: "*",
-        path: "/api/sys/tasks/:id",
+        method: "GET",
+        path: "/api/sys/print-templates",
         scope_id: None,
         mode: PolicyMode::AdminOnly,
     },
     RoutePolicy {
-        method: "*",
-        path: "/api/sys/tasks/:id/toggle_enabled",
+        method: "POST",
+        path: "/api/sys/print-templates",
         scope_id: None,
         mode: PolicyMode::AdminOnly,
     },
     RoutePolicy {
-        method: "GET",
-        path: "/api/sys/tasks/runs/active/progress",
+        method: "PUT",
+        path: "/api/sys/print-templates/:id",
         scope_id: None,
-        mode: PolicyMode::AuthOnly,
+        mode: PolicyMode::AdminOnly,
     },
     RoutePolicy {
-        method: "GET",
-        path: "/api/sys/tasks/:id/progress/:session_id",
+        method: "DELETE",
+        path: "/api/sys/print-templates/:id",
         scope_id: None,
-        mode: PolicyMode::AuthOnly,
+        mode: PolicyMode::AdminOnly,
     },
     RoutePolicy {
         method: "GET",
-        path: "/api/sys/tasks/:id/log/:session_id",
+        path: "/api/sys/config-bundle",
         scope_id: None,
-        mode: PolicyMode::AuthOnly,
+        mode: PolicyMode::AdminOnly,
     },
     RoutePolicy {
-        method: "GET",
-        path: "/api/sys/ext-api/history",
+        method: "POST",
+        path: "/api/sys/config-bundle/import",
         scope_id: None,
         mode: PolicyMode::AdminOnly,
     },
     RoutePolicy {
         method: "GET",
-        path: "/api/sys/ext-api/summary",
+        path: "/api/sys/server-log",
         scope_id: None,
         mode: PolicyMode::AdminOnly,
     },
     RoutePolicy {
-        method: "GET",
-        path: "/api/sys/ext-api/recent",
+        method: "*",
+        path: "/api/sys/auth/oidc",
         scope_id: None,
         mode: PolicyMode::AdminOnly,
     },
@@ -170,6 +675,12 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: None,
         mode: PolicyMode::AdminOnly,
     },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/sys/s3/search",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/system/audit/routes",
@@ -367,6 +878,36 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: Some("a007_marketplace_product"),
         mode: PolicyMode::Auto,
     },
+    RoutePolicy {
+        method: "*",
+        path: "/api/a007/marketplace-product/:id/price-history",
+        scope_id: Some("a007_marketplace_product"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/a007/sales-anomalies",
+        scope_id: Some("a007_marketplace_product"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/a007/sales-anomalies/:id/explain",
+        scope_id: Some("a007_marketplace_product"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/a007/cross-mapping",
+        scope_id: Some("a007_marketplace_product"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/a007/cross-mapping/resolve",
+        scope_id: Some("a007_marketplace_product"),
+        mode: PolicyMode::Auto,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/marketplace_sales",
@@ -427,6 +968,54 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: Some("a012_wb_sales"),
         mode: PolicyMode::Auto,
     },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/a012/wb-sales/compare",
+        scope_id: Some("a012_wb_sales"),
+        mode: PolicyMode::ReadOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/a012/wb-sales/export",
+        scope_id: Some("a012_wb_sales"),
+        mode: PolicyMode::ReadOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/a012/wb-sales/:id/post-preview",
+        scope_id: Some("a012_wb_sales"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/a012/wb-sales/batch-post",
+        scope_id: Some("a012_wb_sales"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/a012/wb-sales/batch-unpost",
+        scope_id: Some("a012_wb_sales"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/a012/wb-sales/:id/finance-links",
+        scope_id: Some("a012_wb_sales"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/a012/wb-sales/:id/finance-links/candidates",
+        scope_id: Some("a012_wb_sales"),
+        mode: PolicyMode::ReadOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/a012/raw/:ref_id/versions",
+        scope_id: Some("a012_wb_sales"),
+        mode: PolicyMode::ReadOnly,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/ym_order",
@@ -451,6 +1040,12 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: Some("a014_ozon_transactions"),
         mode: PolicyMode::Auto,
     },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/a014/ozon-transactions/export",
+        scope_id: Some("a014_ozon_transactions"),
+        mode: PolicyMode::ReadOnly,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/a015/wb-orders",
@@ -463,6 +1058,18 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: Some("a015_wb_orders"),
         mode: PolicyMode::Auto,
     },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/a015/wb-orders/export",
+        scope_id: Some("a015_wb_orders"),
+        mode: PolicyMode::ReadOnly,
+    },
+    RoutePolicy {
+        method: "GET",
+        path: "/api/a015/raw/:ref_id/versions",
+        scope_id: Some("a015_wb_orders"),
+        mode: PolicyMode::ReadOnly,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/ym_returns",
@@ -910,6 +1517,12 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: Some("p900_mp_sales_register"),
         mode: PolicyMode::ReadOnly,
     },
+    RoutePolicy {
+        method: "*",
+        path: "/api/analytics/sku/:id/sparkline",
+        scope_id: Some("p900_mp_sales_register"),
+        mode: PolicyMode::ReadOnly,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/p901/barcode/:barcode",
@@ -964,6 +1577,12 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: Some("p903_wb_finance_report"),
         mode: PolicyMode::ReadOnly,
     },
+    RoutePolicy {
+        method: "*",
+        path: "/api/p903/finance-report/search-by-srid/totals",
+        scope_id: Some("p903_wb_finance_report"),
+        mode: PolicyMode::ReadOnly,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/p903/finance-report/operation-kinds",
@@ -988,6 +1607,18 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: Some("p904_sales_data"),
         mode: PolicyMode::ReadOnly,
     },
+    RoutePolicy {
+        method: "*",
+        path: "/api/p904/return-netting/settings",
+        scope_id: Some("p904_sales_data"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/p904/return-netting/unmatched",
+        scope_id: Some("p904_sales_data"),
+        mode: PolicyMode::ReadOnly,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/p905-commission/list",
@@ -1072,6 +1703,36 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: Some("p912_nomenclature_costs"),
         mode: PolicyMode::ReadOnly,
     },
+    RoutePolicy {
+        method: "*",
+        path: "/api/p918/storage-allocation",
+        scope_id: Some("p918_storage_cost_allocation"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/p918/storage-allocation/settings",
+        scope_id: Some("p918_storage_cost_allocation"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/p918/storage-allocation/rebuild",
+        scope_id: Some("p918_storage_cost_allocation"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/p919/daily-summary",
+        scope_id: Some("p919_daily_sales_summary"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/p919/daily-summary/rebuild",
+        scope_id: Some("p919_daily_sales_summary"),
+        mode: PolicyMode::Auto,
+    },
     // ========================================================================
     // Usecases U501–U508
     // ========================================================================
@@ -1135,6 +1796,30 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: Some("u505_match_nomenclature"),
         mode: PolicyMode::ReadOnly,
     },
+    RoutePolicy {
+        method: "*",
+        path: "/api/u505/suggestions",
+        scope_id: Some("u505_match_nomenclature"),
+        mode: PolicyMode::ReadOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/u505/suggestions/decisions",
+        scope_id: Some("u505_match_nomenclature"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/u505/suggestions/accept-high-confidence",
+        scope_id: Some("u505_match_nomenclature"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/u505/suggestions/accuracy",
+        scope_id: Some("u505_match_nomenclature"),
+        mode: PolicyMode::ReadOnly,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/u506/import/start",
@@ -1183,12 +1868,42 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: Some("u508_repost_documents"),
         mode: PolicyMode::Auto,
     },
+    RoutePolicy {
+        method: "*",
+        path: "/api/u508/repost/period/start",
+        scope_id: Some("u508_repost_documents"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/projections/rebuild",
+        scope_id: Some("u508_repost_documents"),
+        mode: PolicyMode::Auto,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/u508/repost/:session_id/progress",
         scope_id: Some("u508_repost_documents"),
         mode: PolicyMode::ReadOnly,
     },
+    RoutePolicy {
+        method: "*",
+        path: "/api/u509/link/start",
+        scope_id: Some("u509_link_ozon_skus"),
+        mode: PolicyMode::Auto,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/u509/link/:session_id/progress",
+        scope_id: Some("u509_link_ozon_skus"),
+        mode: PolicyMode::ReadOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/u509/unmatched",
+        scope_id: Some("u509_link_ozon_skus"),
+        mode: PolicyMode::ReadOnly,
+    },
     // ========================================================================
     // Dashboards
     // ========================================================================
@@ -1204,6 +1919,48 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: Some("dashboard"),
         mode: PolicyMode::ReadOnly,
     },
+    RoutePolicy {
+        method: "*",
+        path: "/api/dashboards/wb-supply-acceptance",
+        scope_id: Some("dashboard"),
+        mode: PolicyMode::ReadOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/dashboards/pnl-statement",
+        scope_id: Some("dashboard"),
+        mode: PolicyMode::ReadOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/dashboards/margin-scenario",
+        scope_id: Some("dashboard"),
+        mode: PolicyMode::ReadOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/dashboards/sku-launch-cohorts",
+        scope_id: Some("dashboard"),
+        mode: PolicyMode::ReadOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/dashboards/demand-forecast",
+        scope_id: Some("dashboard"),
+        mode: PolicyMode::ReadOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/dashboards/import-coverage",
+        scope_id: Some("dashboard"),
+        mode: PolicyMode::ReadOnly,
+    },
+    RoutePolicy {
+        method: "*",
+        path: "/api/dashboards/sku-pnl",
+        scope_id: Some("dashboard"),
+        mode: PolicyMode::ReadOnly,
+    },
     RoutePolicy {
         method: "*",
         path: "/api/universal-dashboard/execute",
@@ -1490,6 +2247,12 @@ pub static ROUTE_REGISTRY: &[RoutePolicy] = &[
         scope_id: None,
         mode: PolicyMode::AuthOnly,
     },
+    RoutePolicy {
+        method: "POST",
+        path: "/api/quality/checks/:id/acknowledge",
+        scope_id: None,
+        mode: PolicyMode::AdminOnly,
+    },
     // ========================================================================
     // Plugins subsystem — надстройка над платформой (admin-only)
     // ========================================================================
@@ -1689,4 +2452,23 @@ mod tests {
             "stale plugin run route policy should not exist"
         );
     }
+
+    #[test]
+    fn u505_suggestion_routes_are_registered() {
+        let expected = [
+            ("GET", "/api/u505/suggestions"),
+            ("POST", "/api/u505/suggestions/decisions"),
+            ("POST", "/api/u505/suggestions/accept-high-confidence"),
+            ("GET", "/api/u505/suggestions/accuracy"),
+        ];
+
+        for (method, path) in expected {
+            assert!(
+                ROUTE_REGISTRY.iter().any(|policy| policy.path == path
+                    && (policy.method == "*" || policy.method == method)
+                    && policy.scope_id == Some("u505_match_nomenclature")),
+                "missing route policy for {method} {path}"
+            );
+        }
+    }
 }
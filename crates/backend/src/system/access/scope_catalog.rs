@@ -3,6 +3,7 @@
 //! This is the single source of truth for scope labels, descriptions,
 //! and UI metadata. Used by the permission matrix and audit pages.
 
+use contracts::shared::access::{APPROVE_RETURN_DISPOSITION_SCOPE, VIEW_FINANCE_SCOPE};
 use contracts::system::access::{ScopeDescriptor, ScopeType};
 
 pub const SCOPE_CATALOG: &[ScopeDescriptor] = &[
@@ -527,6 +528,26 @@ pub const SCOPE_CATALOG: &[ScopeDescriptor] = &[
         read_label: "Просмотр себестоимости",
         all_label: "Просмотр себестоимости",
     },
+    ScopeDescriptor {
+        scope_id: "p918_storage_cost_allocation",
+        scope_type: ScopeType::Projection,
+        label: "Распределение хранения по SKU",
+        description: "Доли расходов на хранение и приёмку WB по товарам и правила распределения",
+        icon: "box",
+        category: "analytics",
+        read_label: "Просмотр распределения",
+        all_label: "Настройка правил и перераспределение",
+    },
+    ScopeDescriptor {
+        scope_id: "p919_daily_sales_summary",
+        scope_type: ScopeType::Projection,
+        label: "Дневная сводка продаж",
+        description: "Заказы, штуки, выручка и комиссии по дням, маркетплейсам и организациям",
+        icon: "bar-chart",
+        category: "analytics",
+        read_label: "Просмотр сводки",
+        all_label: "Просмотр и пересборка сводки",
+    },
     // ========================================================================
     // USECASES — Imports / Импорт данных
     // ========================================================================
@@ -611,6 +632,16 @@ pub const SCOPE_CATALOG: &[ScopeDescriptor] = &[
         read_label: "Просмотр доступных операций и статуса",
         all_label: "Запуск перепроведения",
     },
+    ScopeDescriptor {
+        scope_id: "u509_link_ozon_skus",
+        scope_type: ScopeType::Usecase,
+        label: "Привязка SKU Ozon",
+        description: "Привязка строк транзакций Ozon к товарам маркетплейса по SKU",
+        icon: "link",
+        category: "imports",
+        read_label: "Просмотр несопоставленных SKU и статуса",
+        all_label: "Запуск привязки",
+    },
     // ========================================================================
     // SYSTEM — Системные функции
     // ========================================================================
@@ -654,8 +685,32 @@ pub const SCOPE_CATALOG: &[ScopeDescriptor] = &[
         read_label: "Просмотр S3-файлов",
         all_label: "Загрузка, скачивание и удаление S3-файлов",
     },
+    ScopeDescriptor {
+        scope_id: VIEW_FINANCE_SCOPE,
+        scope_type: ScopeType::System,
+        label: "Финансовые показатели",
+        description: "Суммы, выплаты и маржа в списках и карточках документов; без доступа эти поля скрыты",
+        icon: "wallet",
+        category: "system",
+        read_label: "Просмотр сумм и маржи",
+        all_label: "Просмотр сумм и маржи",
+    },
+    ScopeDescriptor {
+        scope_id: APPROVE_RETURN_DISPOSITION_SCOPE,
+        scope_type: ScopeType::System,
+        label: "Утверждение решений по возвратам",
+        description: "Утверждение и отклонение решений по физическим возвратам (на склад, утилизация, поставщику)",
+        icon: "check-circle",
+        category: "system",
+        read_label: "Утверждение решений",
+        all_label: "Утверждение решений",
+    },
 ];
 
+/// Scope уровня полей и действий: проверяются при формировании DTO или в хендлере,
+/// а не маршрутами, поэтому в `ROUTE_REGISTRY` не встречаются.
+pub const FIELD_SCOPES: &[&str] = &[VIEW_FINANCE_SCOPE, APPROVE_RETURN_DISPOSITION_SCOPE];
+
 /// Find a scope descriptor by its scope_id.
 pub fn find_scope(scope_id: &str) -> Option<&'static ScopeDescriptor> {
     SCOPE_CATALOG.iter().find(|s| s.scope_id == scope_id)
@@ -0,0 +1,186 @@
+use anyhow::Result;
+use chrono::Utc;
+use contracts::system::announcements::{AnnouncementDto, AnnouncementFeedItem};
+use sea_orm::{ConnectionTrait, DatabaseBackend, QueryResult, Statement};
+
+use crate::shared::data::db::get_connection;
+
+/// Поля объявления для записи (уже проверенные сервисом).
+pub struct AnnouncementFields<'a> {
+    pub title: &'a str,
+    pub body: &'a str,
+    pub version: Option<&'a str>,
+    pub tab_key: Option<&'a str>,
+    pub published_at: Option<&'a str>,
+}
+
+fn dto_from_row(row: &QueryResult) -> Option<AnnouncementDto> {
+    Some(AnnouncementDto {
+        id: row.try_get("", "id").ok()?,
+        title: row.try_get("", "title").ok()?,
+        body: row.try_get("", "body").ok()?,
+        version: row.try_get("", "version").ok()?,
+        tab_key: row.try_get("", "tab_key").ok()?,
+        published_at: row.try_get("", "published_at").ok()?,
+        created_by: row.try_get("", "created_by").ok()?,
+        created_at: row.try_get("", "created_at").ok()?,
+        updated_at: row.try_get("", "updated_at").ok()?,
+    })
+}
+
+const SELECT_COLUMNS: &str =
+    "SELECT id, title, body, version, tab_key, published_at, created_by, created_at, updated_at
+     FROM sys_announcements";
+
+/// Все объявления: сначала черновики, затем опубликованные от новых к старым.
+pub async fn list_all() -> Result<Vec<AnnouncementDto>> {
+    let rows = get_connection()
+        .query_all(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            format!(
+                "{SELECT_COLUMNS} ORDER BY published_at IS NOT NULL, published_at DESC, created_at DESC"
+            ),
+        ))
+        .await?;
+    Ok(rows.iter().filter_map(dto_from_row).collect())
+}
+
+pub async fn get_by_id(id: &str) -> Result<Option<AnnouncementDto>> {
+    let row = get_connection()
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            format!("{SELECT_COLUMNS} WHERE id = ?"),
+            [id.into()],
+        ))
+        .await?;
+    Ok(row.as_ref().and_then(dto_from_row))
+}
+
+pub async fn insert(id: &str, fields: &AnnouncementFields<'_>, created_by: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    get_connection()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO sys_announcements
+                (id, title, body, version, tab_key, published_at, created_by, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            [
+                id.into(),
+                fields.title.into(),
+                fields.body.into(),
+                fields.version.map(str::to_string).into(),
+                fields.tab_key.map(str::to_string).into(),
+                fields.published_at.map(str::to_string).into(),
+                created_by.into(),
+                now.clone().into(),
+                now.into(),
+            ],
+        ))
+        .await?;
+    Ok(())
+}
+
+/// Возвращает `false`, если объявления с таким id нет.
+pub async fn update(id: &str, fields: &AnnouncementFields<'_>) -> Result<bool> {
+    let result = get_connection()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_announcements
+             SET title = ?, body = ?, version = ?, tab_key = ?, published_at = ?, updated_at = ?
+             WHERE id = ?",
+            [
+                fields.title.into(),
+                fields.body.into(),
+                fields.version.map(str::to_string).into(),
+                fields.tab_key.map(str::to_string).into(),
+                fields.published_at.map(str::to_string).into(),
+                Utc::now().to_rfc3339().into(),
+                id.into(),
+            ],
+        ))
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Удаляет объявление вместе с отметками о прочтении.
+pub async fn delete(id: &str) -> Result<bool> {
+    let conn = get_connection();
+    conn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM sys_announcement_reads WHERE announcement_id = ?",
+        [id.into()],
+    ))
+    .await?;
+    let result = conn
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM sys_announcements WHERE id = ?",
+            [id.into()],
+        ))
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Опубликованные объявления с отметкой о прочтении пользователем.
+pub async fn list_feed(user_id: &str, limit: u64) -> Result<Vec<AnnouncementFeedItem>> {
+    let rows = get_connection()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT a.id, a.title, a.body, a.version, a.tab_key, a.published_at,
+                    r.read_at IS NOT NULL AS is_read
+             FROM sys_announcements a
+             LEFT JOIN sys_announcement_reads r
+               ON r.announcement_id = a.id AND r.user_id = ?
+             WHERE a.published_at IS NOT NULL
+             ORDER BY a.published_at DESC
+             LIMIT ?",
+            [user_id.into(), (limit as i64).into()],
+        ))
+        .await?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let is_read: i32 = row.try_get("", "is_read").ok()?;
+            Some(AnnouncementFeedItem {
+                id: row.try_get("", "id").ok()?,
+                title: row.try_get("", "title").ok()?,
+                body: row.try_get("", "body").ok()?,
+                version: row.try_get("", "version").ok()?,
+                tab_key: row.try_get("", "tab_key").ok()?,
+                published_at: row.try_get("", "published_at").ok()?,
+                read: is_read != 0,
+            })
+        })
+        .collect())
+}
+
+pub async fn count_unread(user_id: &str) -> Result<i64> {
+    let row = get_connection()
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT COUNT(*) AS cnt FROM sys_announcements a
+             WHERE a.published_at IS NOT NULL
+               AND NOT EXISTS (
+                   SELECT 1 FROM sys_announcement_reads r
+                   WHERE r.announcement_id = a.id AND r.user_id = ?
+               )",
+            [user_id.into()],
+        ))
+        .await?;
+    Ok(row
+        .and_then(|r| r.try_get::<i64>("", "cnt").ok())
+        .unwrap_or(0))
+}
+
+/// Отмечает прочитанными все опубликованные объявления.
+pub async fn mark_all_read(user_id: &str) -> Result<()> {
+    get_connection()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT OR IGNORE INTO sys_announcement_reads (user_id, announcement_id, read_at)
+             SELECT ?, id, ? FROM sys_announcements WHERE published_at IS NOT NULL",
+            [user_id.into(), Utc::now().to_rfc3339().into()],
+        ))
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use contracts::system::announcements::{
+    AnnouncementDto, AnnouncementFeedDto, AnnouncementUpsertRequest, ANNOUNCEMENT_TITLE_MAX_LEN,
+};
+use uuid::Uuid;
+
+use super::repository::{self, AnnouncementFields};
+
+/// Сколько последних объявлений показывает панель «Что нового»
+const FEED_LIMIT: u64 = 30;
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Обрезает пробелы и проверяет обязательные поля запроса.
+fn normalize(req: AnnouncementUpsertRequest) -> Result<AnnouncementUpsertRequest> {
+    let title = req.title.trim().to_string();
+    if title.is_empty() {
+        return Err(anyhow!("Invalid announcement: title is required"));
+    }
+    if title.chars().count() > ANNOUNCEMENT_TITLE_MAX_LEN {
+        return Err(anyhow!(
+            "Invalid announcement: title is longer than {} characters",
+            ANNOUNCEMENT_TITLE_MAX_LEN
+        ));
+    }
+    let body = req.body.trim().to_string();
+    if body.is_empty() {
+        return Err(anyhow!("Invalid announcement: body is required"));
+    }
+    Ok(AnnouncementUpsertRequest {
+        title,
+        body,
+        version: non_empty(req.version),
+        tab_key: non_empty(req.tab_key),
+        published: req.published,
+    })
+}
+
+/// Дата публикации: при повторном сохранении опубликованного объявления не меняется,
+/// чтобы правка опечатки не поднимала его наверх ленты.
+fn published_at(published: bool, previous: Option<&str>) -> Option<String> {
+    published.then(|| {
+        previous
+            .map(str::to_string)
+            .unwrap_or_else(|| Utc::now().to_rfc3339())
+    })
+}
+
+pub async fn list() -> Result<Vec<AnnouncementDto>> {
+    repository::list_all().await
+}
+
+pub async fn create(req: AnnouncementUpsertRequest, author_id: &str) -> Result<AnnouncementDto> {
+    let req = normalize(req)?;
+    let id = Uuid::new_v4().to_string();
+    let published_at = published_at(req.published, None);
+    repository::insert(
+        &id,
+        &AnnouncementFields {
+            title: &req.title,
+            body: &req.body,
+            version: req.version.as_deref(),
+            tab_key: req.tab_key.as_deref(),
+            published_at: published_at.as_deref(),
+        },
+        author_id,
+    )
+    .await?;
+    repository::get_by_id(&id)
+        .await?
+        .ok_or_else(|| anyhow!("Announcement not found"))
+}
+
+pub async fn update(id: &str, req: AnnouncementUpsertRequest) -> Result<AnnouncementDto> {
+    let req = normalize(req)?;
+    let existing = repository::get_by_id(id)
+        .await?
+        .ok_or_else(|| anyhow!("Announcement not found"))?;
+    let published_at = published_at(req.published, existing.published_at.as_deref());
+    repository::update(
+        id,
+        &AnnouncementFields {
+            title: &req.title,
+            body: &req.body,
+            version: req.version.as_deref(),
+            tab_key: req.tab_key.as_deref(),
+            published_at: published_at.as_deref(),
+        },
+    )
+    .await?;
+    repository::get_by_id(id)
+        .await?
+        .ok_or_else(|| anyhow!("Announcement not found"))
+}
+
+pub async fn delete(id: &str) -> Result<()> {
+    if repository::delete(id).await? {
+        Ok(())
+    } else {
+        Err(anyhow!("Announcement not found"))
+    }
+}
+
+pub async fn feed(user_id: &str) -> Result<AnnouncementFeedDto> {
+    Ok(AnnouncementFeedDto {
+        items: repository::list_feed(user_id, FEED_LIMIT).await?,
+        unread: repository::count_unread(user_id).await?,
+    })
+}
+
+pub async fn mark_all_read(user_id: &str) -> Result<()> {
+    repository::mark_all_read(user_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(title: &str, body: &str) -> AnnouncementUpsertRequest {
+        AnnouncementUpsertRequest {
+            title: title.to_string(),
+            body: body.to_string(),
+            version: Some("  ".to_string()),
+            tab_key: Some(" a012_wb_sales ".to_string()),
+            published: true,
+        }
+    }
+
+    #[test]
+    fn normalize_trims_and_drops_blank_optionals() {
+        let req = normalize(request("  Новое  ", " Текст ")).unwrap();
+        assert_eq!(req.title, "Новое");
+        assert_eq!(req.body, "Текст");
+        assert_eq!(req.version, None);
+        assert_eq!(req.tab_key.as_deref(), Some("a012_wb_sales"));
+    }
+
+    #[test]
+    fn normalize_rejects_empty_title_and_body() {
+        assert!(normalize(request(" ", "Текст")).is_err());
+        assert!(normalize(request("Новое", "\n")).is_err());
+        let long = "я".repeat(ANNOUNCEMENT_TITLE_MAX_LEN + 1);
+        assert!(normalize(request(&long, "Текст")).is_err());
+    }
+
+    #[test]
+    fn republishing_keeps_original_date() {
+        let first = "2026-10-01T10:00:00+00:00";
+        assert_eq!(published_at(true, Some(first)).as_deref(), Some(first));
+        assert!(published_at(true, None).is_some());
+        assert_eq!(published_at(false, Some(first)), None);
+    }
+}
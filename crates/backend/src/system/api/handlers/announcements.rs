@@ -0,0 +1,74 @@
+//! Хендлеры объявлений «Что нового»: лента текущего пользователя и управление
+//! объявлениями (только админ).
+
+use axum::{extract::Path, http::StatusCode, Json};
+use contracts::system::announcements::{
+    AnnouncementDto, AnnouncementFeedDto, AnnouncementUpsertRequest,
+};
+
+use crate::system::announcements::service;
+use crate::system::auth::extractor::CurrentUser;
+
+fn map_error(err: anyhow::Error) -> (StatusCode, String) {
+    let message = err.to_string();
+    if message.starts_with("Invalid") {
+        (StatusCode::BAD_REQUEST, message)
+    } else if message.contains("not found") {
+        (StatusCode::NOT_FOUND, message)
+    } else {
+        tracing::error!("Announcements API error: {}", message);
+        (StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+/// GET /api/system/announcements — опубликованные объявления с отметкой о прочтении.
+pub async fn feed(
+    CurrentUser(claims): CurrentUser,
+) -> Result<Json<AnnouncementFeedDto>, (StatusCode, String)> {
+    service::feed(&claims.sub)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// POST /api/system/announcements/read — отметить все как прочитанные.
+pub async fn mark_all_read(
+    CurrentUser(claims): CurrentUser,
+) -> Result<StatusCode, (StatusCode, String)> {
+    service::mark_all_read(&claims.sub)
+        .await
+        .map(|_| StatusCode::OK)
+        .map_err(map_error)
+}
+
+/// GET /api/sys/announcements — все объявления, включая черновики.
+pub async fn list() -> Result<Json<Vec<AnnouncementDto>>, (StatusCode, String)> {
+    service::list().await.map(Json).map_err(map_error)
+}
+
+/// POST /api/sys/announcements — создать объявление.
+pub async fn create(
+    CurrentUser(claims): CurrentUser,
+    Json(req): Json<AnnouncementUpsertRequest>,
+) -> Result<Json<AnnouncementDto>, (StatusCode, String)> {
+    service::create(req, &claims.sub)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// PUT /api/sys/announcements/:id — изменить, опубликовать или снять с публикации.
+pub async fn update(
+    Path(id): Path<String>,
+    Json(req): Json<AnnouncementUpsertRequest>,
+) -> Result<Json<AnnouncementDto>, (StatusCode, String)> {
+    service::update(&id, req).await.map(Json).map_err(map_error)
+}
+
+/// DELETE /api/sys/announcements/:id
+pub async fn delete(Path(id): Path<String>) -> Result<StatusCode, (StatusCode, String)> {
+    service::delete(&id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(map_error)
+}
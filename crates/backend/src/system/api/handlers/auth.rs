@@ -1,7 +1,9 @@
 use axum::{extract::Json, http::StatusCode};
+use chrono::Utc;
 use contracts::system::auth::{
     LoginRequest, LoginResponse, RefreshRequest, RefreshResponse, UserInfo,
 };
+use contracts::system::users::User;
 
 use crate::system::access::resolver;
 use crate::system::auth::extractor::CurrentUser;
@@ -15,6 +17,14 @@ pub async fn login(Json(request): Json<LoginRequest>) -> Result<Json<LoginRespon
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::UNAUTHORIZED)?;
 
+    login_response(user)
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Выдаёт access/refresh-токены пользователю, прошедшему проверку (пароль или OIDC).
+pub(crate) async fn login_response(user: User) -> anyhow::Result<LoginResponse> {
     // Resolve primary role and scopes
     let primary_role = resolver::get_primary_role_code(&user.id)
         .await
@@ -26,18 +36,14 @@ pub async fn login(Json(request): Json<LoginRequest>) -> Result<Json<LoginRespon
 
     // Generate tokens
     let access_token =
-        jwt::generate_access_token(&user.id, &user.username, user.is_admin, &primary_role)
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        jwt::generate_access_token(&user.id, &user.username, user.is_admin, &primary_role).await?;
 
     let refresh_token = jwt::generate_refresh_token();
 
     // Store refresh token in database
-    store_refresh_token(&user.id, &refresh_token)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    store_refresh_token(&user.id, &refresh_token).await?;
 
-    let response = LoginResponse {
+    Ok(LoginResponse {
         access_token,
         refresh_token,
         user: UserInfo {
@@ -49,9 +55,7 @@ pub async fn login(Json(request): Json<LoginRequest>) -> Result<Json<LoginRespon
             primary_role,
             scopes,
         },
-    };
-
-    Ok(Json(response))
+    })
 }
 
 /// Refresh token handler
@@ -69,6 +73,11 @@ pub async fn refresh(
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::UNAUTHORIZED)?;
 
+    // Отключённая или вышедшая за срок доступа учётная запись не продлевает сессию
+    if !user_service::is_access_allowed(&user, Utc::now().date_naive()) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
     let primary_role = resolver::get_primary_role_code(&user.id)
         .await
         .unwrap_or_else(|_| "viewer".to_string());
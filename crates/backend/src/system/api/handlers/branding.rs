@@ -0,0 +1,60 @@
+//! Хендлеры брендирования организаций: публичное чтение для текущего хоста
+//! и управление настройками (только админ).
+
+use axum::{
+    extract::Path,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use contracts::system::branding::{BrandingDto, OrgBrandingDto, OrgBrandingUpsertRequest};
+
+use crate::system::branding::service;
+
+fn map_error(err: anyhow::Error) -> StatusCode {
+    let message = err.to_string();
+    if message.contains("not found") {
+        StatusCode::NOT_FOUND
+    } else if message.contains("Invalid") {
+        StatusCode::BAD_REQUEST
+    } else {
+        tracing::error!("Branding API error: {}", message);
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// Хост, с которого открыт фронтенд: за прокси — `X-Forwarded-Host`.
+pub(crate) fn request_host(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("x-forwarded-host")
+        .or_else(|| headers.get(axum::http::header::HOST))
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(',').next().unwrap_or(v).to_string())
+}
+
+/// GET /api/system/branding — брендирование для хоста запроса (доступно до входа).
+pub async fn get_current(headers: HeaderMap) -> Result<Json<BrandingDto>, StatusCode> {
+    service::resolve(request_host(&headers).as_deref())
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// GET /api/sys/branding — все организации и их брендирование.
+pub async fn list() -> Result<Json<Vec<OrgBrandingDto>>, StatusCode> {
+    service::list().await.map(Json).map_err(map_error)
+}
+
+/// PUT /api/sys/branding — сохранить брендирование организации.
+pub async fn upsert(
+    Json(req): Json<OrgBrandingUpsertRequest>,
+) -> Result<Json<OrgBrandingDto>, StatusCode> {
+    service::upsert(req).await.map(Json).map_err(map_error)
+}
+
+/// DELETE /api/sys/branding/:organization_id — вернуть организации стандартный вид.
+pub async fn delete(Path(organization_id): Path<String>) -> Result<StatusCode, StatusCode> {
+    service::delete(&organization_id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(map_error)
+}
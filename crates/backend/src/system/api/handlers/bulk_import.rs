@@ -0,0 +1,108 @@
+//! Хендлеры массовой загрузки истории (страница «Массовая загрузка»).
+
+use axum::{
+    body::Body,
+    extract::Query,
+    http::{header, HeaderMap, StatusCode},
+    Json,
+};
+use contracts::system::bulk_import::BulkImportResultDto;
+use futures_util::StreamExt;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::shared::ndjson::NdjsonLines;
+use crate::usecases::u504_import_from_wildberries::processors::finance_report_bulk::FinanceReportBulkImport;
+
+#[derive(Debug, Deserialize)]
+pub struct BulkImportQuery {
+    pub connection_id: String,
+    /// После загрузки перепровести период (a004-ссылки, GL, p914).
+    #[serde(default)]
+    pub rebuild: bool,
+}
+
+/// POST /api/sys/bulk-import/p903?connection_id=...&rebuild=false
+///
+/// Тело — NDJSON строк финансового отчёта WB, можно сжатое gzip. Читается
+/// потоком, строки пишутся пачками; ошибки разбора и записи возвращаются в
+/// отчёте, а не прерывают загрузку.
+pub async fn import_p903(
+    Query(query): Query<BulkImportQuery>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Json<BulkImportResultDto>, StatusCode> {
+    let connection_id =
+        Uuid::parse_str(&query.connection_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let connection = crate::domain::a006_connection_mp::service::get_by_id(connection_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load connection {}: {}", connection_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let gzip = headers
+        .get(header::CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("gzip"));
+
+    let mut lines = NdjsonLines::new(gzip);
+    let mut import = FinanceReportBulkImport::new(&connection);
+    let mut stream = body.into_data_stream();
+    let mut stream_ok = true;
+    while let Some(next) = stream.next().await {
+        let parsed = next
+            .map_err(|e| anyhow::anyhow!("Обрыв загрузки: {}", e))
+            .and_then(|bytes| lines.feed(&bytes));
+        match parsed {
+            Ok(batch) => {
+                for line in batch {
+                    import.push_line(&line).await;
+                }
+            }
+            Err(e) => {
+                import.stream_error(e.to_string());
+                stream_ok = false;
+                break;
+            }
+        }
+    }
+    if stream_ok {
+        match lines.finish() {
+            Ok(batch) => {
+                for line in batch {
+                    import.push_line(&line).await;
+                }
+            }
+            Err(e) => import.stream_error(e.to_string()),
+        }
+    }
+
+    let mut result = import.finish().await;
+    tracing::info!(
+        "p903 bulk import for {}: {} lines, {} upserted, {} rejected",
+        connection_id,
+        result.total_lines,
+        result.upserted,
+        result.rejected
+    );
+
+    if query.rebuild {
+        if let (Some(from), Some(to)) = (result.date_from.clone(), result.date_to.clone()) {
+            match crate::projections::p903_wb_finance_report::service::rebuild_range_from_existing(
+                &from, &to,
+            )
+            .await
+            {
+                Ok(days) => result.rebuilt_days = Some(days),
+                Err(e) => {
+                    tracing::error!("p903 bulk import: rebuild {}..{} failed: {}", from, to, e);
+                    return Err(StatusCode::INTERNAL_SERVER_ERROR);
+                }
+            }
+        }
+    }
+
+    Ok(Json(result))
+}
@@ -0,0 +1,115 @@
+//! Хендлеры журнала массовых операций и их отмены (страница «История операций»).
+
+use axum::{
+    extract::{Path, Query},
+    Json,
+};
+use contracts::system::bulk_ops::{
+    parse_id_list, BulkLookupRequest, BulkLookupResponse, BulkOperationListResponse,
+    BulkReassignReportDto, BulkReassignRequest, BulkUndoResultDto, BulkUndoWindowDto,
+};
+use serde::Deserialize;
+
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::bulk_ops::{lookup, reassign, service};
+use crate::system::org_context::ActiveOrganization;
+
+#[derive(Deserialize)]
+pub struct BulkOperationListQuery {
+    pub limit: Option<u64>,
+}
+
+/// GET /api/sys/bulk-operations — последние массовые операции.
+pub async fn list(
+    Query(q): Query<BulkOperationListQuery>,
+) -> Result<Json<BulkOperationListResponse>, axum::http::StatusCode> {
+    service::list(q.limit).await.map(Json).map_err(|e| {
+        tracing::error!("Failed to list bulk operations: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// POST /api/sys/bulk-operations/:id/undo — отмена операции в пределах окна.
+pub async fn undo(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<BulkUndoResultDto>, axum::http::StatusCode> {
+    let internal = |e: anyhow::Error| {
+        tracing::error!("Failed to undo bulk operation: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    };
+    if service::get(&id).await.map_err(internal)?.is_none() {
+        return Err(axum::http::StatusCode::NOT_FOUND);
+    }
+    match service::undo(&id, &claims.sub).await.map_err(internal)? {
+        Some(result) => Ok(Json(result)),
+        // Окно истекло или операция уже отменена.
+        None => Err(axum::http::StatusCode::CONFLICT),
+    }
+}
+
+/// GET /api/sys/bulk-operations/undo-window
+pub async fn get_undo_window() -> Result<Json<BulkUndoWindowDto>, axum::http::StatusCode> {
+    crate::system::settings::service::get_bulk_undo_window_minutes()
+        .await
+        .map(|minutes| Json(BulkUndoWindowDto { minutes }))
+        .map_err(|e| {
+            tracing::error!("Failed to get bulk undo window: {}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// POST /api/sys/bulk-operations/undo-window
+pub async fn set_undo_window(
+    Json(dto): Json<BulkUndoWindowDto>,
+) -> Result<Json<BulkUndoWindowDto>, axum::http::StatusCode> {
+    let result = async {
+        crate::system::settings::service::set_bulk_undo_window_minutes(dto.minutes).await?;
+        crate::system::settings::service::get_bulk_undo_window_minutes().await
+    }
+    .await;
+    result
+        .map(|minutes| Json(BulkUndoWindowDto { minutes }))
+        .map_err(|e| {
+            tracing::error!("Failed to set bulk undo window: {}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// POST /api/sys/bulk-operations/lookup — поиск документов по вставленному списку номеров.
+pub async fn lookup(
+    active_org: ActiveOrganization,
+    Json(req): Json<BulkLookupRequest>,
+) -> Result<Json<BulkLookupResponse>, axum::http::StatusCode> {
+    let ids = parse_id_list(&req.ids.join("\n"));
+    if ids.len() > contracts::system::bulk_ops::BULK_LOOKUP_MAX_IDS {
+        return Err(axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+    }
+    lookup::lookup(&ids, active_org.0.as_deref())
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("Failed to look up documents by ID list: {}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// POST /api/sys/bulk-operations/reassign — переназначение организации / маркетплейса
+/// в исторических документах (`dry_run` — только отчёт).
+pub async fn reassign(
+    CurrentUser(claims): CurrentUser,
+    Json(req): Json<BulkReassignRequest>,
+) -> Result<Json<BulkReassignReportDto>, (axum::http::StatusCode, String)> {
+    reassign::reassign(&req, &claims.sub)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            let message = e.to_string();
+            if message.contains("Invalid") {
+                (axum::http::StatusCode::BAD_REQUEST, message)
+            } else {
+                tracing::error!("Failed to reassign documents: {}", message);
+                (axum::http::StatusCode::INTERNAL_SERVER_ERROR, message)
+            }
+        })
+}
@@ -0,0 +1,31 @@
+//! Хендлеры пакета конфигурации (только админ).
+
+use axum::{http::StatusCode, Json};
+use contracts::system::config_bundle::{ConfigBundle, ConfigImportRequest, ConfigImportResult};
+
+use crate::system::config_bundle::service;
+
+fn map_error(err: anyhow::Error) -> (StatusCode, String) {
+    let message = err.to_string();
+    if message.contains("Invalid") {
+        (StatusCode::BAD_REQUEST, message)
+    } else {
+        tracing::error!("Config bundle API error: {}", message);
+        (StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+/// GET /api/sys/config-bundle — выгрузить пакет конфигурации.
+pub async fn export() -> Result<Json<ConfigBundle>, (StatusCode, String)> {
+    service::export().await.map(Json).map_err(map_error)
+}
+
+/// POST /api/sys/config-bundle/import — применить пакет (или проверить при `dry_run`).
+pub async fn import(
+    Json(req): Json<ConfigImportRequest>,
+) -> Result<Json<ConfigImportResult>, (StatusCode, String)> {
+    service::import(req.bundle, req.dry_run)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
@@ -0,0 +1,64 @@
+//! Свежесть данных по источникам и настройка SLA (страница «Свежесть данных»).
+
+use axum::{http::StatusCode, Json};
+use contracts::system::data_freshness::{
+    DataFreshnessDto, DataFreshnessSlaSettings, FreshnessDataset,
+};
+
+use crate::system::data_freshness;
+use crate::system::settings::service as settings_service;
+
+/// Верхняя граница SLA, часов (90 дней).
+const MAX_SLA_HOURS: i64 = 90 * 24;
+
+fn internal(err: anyhow::Error) -> (StatusCode, String) {
+    tracing::error!("Data freshness API error: {}", err);
+    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+}
+
+/// GET /api/system/data-freshness — все источники с последним импортом и SLA.
+pub async fn list() -> Result<Json<Vec<DataFreshnessDto>>, (StatusCode, String)> {
+    data_freshness::list(&FreshnessDataset::all())
+        .await
+        .map(Json)
+        .map_err(internal)
+}
+
+/// GET /api/sys/data-freshness/sla
+pub async fn get_sla() -> Result<Json<DataFreshnessSlaSettings>, (StatusCode, String)> {
+    settings_service::get_data_freshness_sla()
+        .await
+        .map(Json)
+        .map_err(internal)
+}
+
+/// PUT /api/sys/data-freshness/sla — переопределения SLA; 0 — значение по умолчанию.
+pub async fn save_sla(
+    Json(mut settings): Json<DataFreshnessSlaSettings>,
+) -> Result<Json<DataFreshnessSlaSettings>, (StatusCode, String)> {
+    if let Some(code) = settings
+        .sla_hours
+        .keys()
+        .find(|code| FreshnessDataset::from_code(code).is_none())
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Invalid dataset: {}", code),
+        ));
+    }
+    if settings
+        .sla_hours
+        .values()
+        .any(|hours| !(0..=MAX_SLA_HOURS).contains(hours))
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Invalid SLA: expected 0..{} hours", MAX_SLA_HOURS),
+        ));
+    }
+    settings.sla_hours.retain(|_, hours| *hours > 0);
+    settings_service::set_data_freshness_sla(&settings)
+        .await
+        .map_err(internal)?;
+    Ok(Json(settings))
+}
@@ -0,0 +1,57 @@
+//! Хендлеры шаблонов описаний документов (только админ).
+
+use axum::{extract::Path, http::StatusCode, Json};
+use contracts::system::description_templates::{
+    ApplyDescriptionTemplateResponse, DescriptionTemplateDto, DescriptionTemplateUpsertRequest,
+};
+
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::description_templates::service;
+
+fn map_error(err: anyhow::Error) -> StatusCode {
+    let message = err.to_string();
+    if message.contains("not found") {
+        StatusCode::NOT_FOUND
+    } else if message.contains("Invalid") {
+        StatusCode::BAD_REQUEST
+    } else {
+        tracing::error!("Description templates API error: {}", message);
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// GET /api/sys/description-templates — шаблоны всех поддерживаемых агрегатов.
+pub async fn list() -> Result<Json<Vec<DescriptionTemplateDto>>, StatusCode> {
+    service::list().await.map(Json).map_err(map_error)
+}
+
+/// PUT /api/sys/description-templates/:entity_type — сохранить шаблон.
+pub async fn upsert(
+    CurrentUser(claims): CurrentUser,
+    Path(entity_type): Path<String>,
+    Json(req): Json<DescriptionTemplateUpsertRequest>,
+) -> Result<Json<DescriptionTemplateDto>, StatusCode> {
+    service::upsert(&entity_type, &req.template, &claims.username)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// DELETE /api/sys/description-templates/:entity_type — вернуть описание по умолчанию.
+pub async fn delete(Path(entity_type): Path<String>) -> Result<StatusCode, StatusCode> {
+    service::delete(&entity_type)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(map_error)
+}
+
+/// POST /api/sys/description-templates/:entity_type/apply — пересобрать описания
+/// уже загруженных документов.
+pub async fn apply(
+    Path(entity_type): Path<String>,
+) -> Result<Json<ApplyDescriptionTemplateResponse>, StatusCode> {
+    service::apply(&entity_type)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
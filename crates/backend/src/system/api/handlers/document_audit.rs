@@ -0,0 +1,47 @@
+//! Вкладка «История» карточки документа: журнал действий из `sys_audit_log`.
+
+use axum::{extract::Path, http::StatusCode, Json};
+use contracts::system::document_audit::DocumentAuditEntryDto;
+
+use crate::shared::data::projection_archive;
+use crate::system::access::resolver;
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::document_audit::{self, repository};
+use crate::system::org_context::ActiveOrganization;
+
+/// GET /api/system/document-audit/:entity/:id — `entity` — scope агрегата
+/// (`a012_wb_sales`). Видит тот, у кого есть хотя бы чтение этого агрегата;
+/// история документа другой организации — как у несуществующего документа.
+pub async fn list(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+    Path((entity, id)): Path<(String, String)>,
+) -> Result<Json<Vec<DocumentAuditEntryDto>>, (StatusCode, String)> {
+    let Some(table) = document_audit::table_for_scope(&entity) else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Неизвестный агрегат: {}", entity),
+        ));
+    };
+
+    let allowed = resolver::can_read_scope(&claims, &entity)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !allowed {
+        return Err((StatusCode::FORBIDDEN, "Нет доступа к документу".to_string()));
+    }
+
+    if active_org.0.is_some() {
+        let organization = repository::snapshot(&projection_archive::source(table, None), &id)
+            .await
+            .and_then(|row| document_audit::row_organization(&row));
+        if organization.is_some_and(|organization_id| !active_org.allows(&organization_id)) {
+            return Err((StatusCode::NOT_FOUND, "Документ не найден".to_string()));
+        }
+    }
+
+    repository::list(&entity, &id).await.map(Json).map_err(|e| {
+        tracing::error!("Failed to load document audit for {} {}: {}", entity, id, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+    })
+}
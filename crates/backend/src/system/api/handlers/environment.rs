@@ -0,0 +1,24 @@
+//! Режим окружения для баннера и выдача токенов подтверждения опасных операций.
+
+use axum::{http::StatusCode, Json};
+use contracts::system::environment::{
+    DangerConfirmRequest, DangerConfirmResponse, EnvironmentInfoDto,
+};
+
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::environment;
+
+/// GET /api/system/environment — режим окружения (без авторизации: баннер нужен и на входе).
+pub async fn get_info() -> Json<EnvironmentInfoDto> {
+    Json(environment::info())
+}
+
+/// POST /api/sys/environment/confirm — токен подтверждения опасной операции.
+pub async fn confirm(
+    CurrentUser(claims): CurrentUser,
+    Json(req): Json<DangerConfirmRequest>,
+) -> Result<Json<DangerConfirmResponse>, (StatusCode, String)> {
+    environment::issue_token(&claims.sub, &req)
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))
+}
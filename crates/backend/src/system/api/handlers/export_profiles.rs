@@ -0,0 +1,107 @@
+//! Хендлеры профилей выгрузок текущего пользователя.
+
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use chrono::Utc;
+use contracts::system::export_profiles::{
+    ExportProfileDto, ExportProfileListResponse, ExportProfileSaveDto,
+};
+use contracts::system::exports::ExportJobDto;
+
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::export_profiles::service;
+use crate::system::quotas::service::{CurrentOrganization, QuotaExceeded};
+
+/// GET /api/system/export-profiles — профили текущего пользователя.
+pub async fn list(
+    CurrentUser(claims): CurrentUser,
+) -> Result<Json<ExportProfileListResponse>, StatusCode> {
+    service::list_for_user(&claims.sub)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("Failed to list export profiles: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// POST /api/system/export-profiles
+pub async fn create(
+    CurrentUser(claims): CurrentUser,
+    organization: Option<Extension<CurrentOrganization>>,
+    Json(dto): Json<ExportProfileSaveDto>,
+) -> Result<Json<ExportProfileDto>, (StatusCode, String)> {
+    let next_run_at = service::validate(&dto, Utc::now())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    service::create(
+        &dto,
+        next_run_at,
+        &claims.sub,
+        organization.map(|Extension(CurrentOrganization(id))| id),
+    )
+    .await
+    .map(Json)
+    .map_err(|e| {
+        tracing::error!("Failed to create export profile: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+    })
+}
+
+/// PUT /api/system/export-profiles/:id
+pub async fn update(
+    CurrentUser(claims): CurrentUser,
+    organization: Option<Extension<CurrentOrganization>>,
+    Path(id): Path<String>,
+    Json(dto): Json<ExportProfileSaveDto>,
+) -> Result<Json<ExportProfileDto>, (StatusCode, String)> {
+    let next_run_at = service::validate(&dto, Utc::now())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    service::update(
+        &id,
+        &dto,
+        next_run_at,
+        &claims.sub,
+        organization.map(|Extension(CurrentOrganization(id))| id),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Failed to update export profile {}: {}", id, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+    })?
+    .map(Json)
+    .ok_or((StatusCode::NOT_FOUND, String::new()))
+}
+
+/// DELETE /api/system/export-profiles/:id
+pub async fn delete(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<(), StatusCode> {
+    match service::delete(&id, &claims.sub).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to delete export profile {}: {}", id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// POST /api/system/export-profiles/:id/run — запустить сейчас; файл появится в «Мои выгрузки».
+pub async fn run(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<ExportJobDto>, (StatusCode, String)> {
+    match service::run(&id, &claims.sub).await {
+        Ok(Some(job)) => Ok(Json(job)),
+        Ok(None) => Err((StatusCode::NOT_FOUND, String::new())),
+        Err(e) => {
+            if let Some(exceeded) = e.downcast_ref::<QuotaExceeded>() {
+                return Err((StatusCode::TOO_MANY_REQUESTS, exceeded.to_string()));
+            }
+            tracing::warn!("Failed to run export profile {}: {}", id, e);
+            Err((StatusCode::BAD_REQUEST, e.to_string()))
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Хендлеры «Мои выгрузки»: задачи фоновой выгрузки текущего пользователя.
+
+use axum::body::Body;
+use axum::extract::Path;
+use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use axum::http::{HeaderValue, Response, StatusCode};
+use axum::Json;
+use contracts::system::exports::ExportJobListResponse;
+
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::exports::service;
+use crate::system::s3::service::sanitize_filename;
+
+/// GET /api/system/exports — выгрузки текущего пользователя.
+pub async fn list(
+    CurrentUser(claims): CurrentUser,
+) -> Result<Json<ExportJobListResponse>, StatusCode> {
+    service::list_for_user(&claims.sub)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("Failed to list export jobs: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// GET /api/system/exports/:id/download
+pub async fn download(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Response<Body>, StatusCode> {
+    let download = service::download(&id, &claims.sub)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to download export {}: {}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let filename = sanitize_filename(&download.file.original_filename);
+    let mut response = Response::new(Body::from(download.bytes));
+    let headers = response.headers_mut();
+    headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_str(
+            download
+                .content_type
+                .as_deref()
+                .unwrap_or("text/csv; charset=utf-8"),
+        )
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+    );
+    headers.insert(
+        CONTENT_DISPOSITION,
+        HeaderValue::from_str(&format!("attachment; filename=\"{}\"", filename))
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+    );
+    Ok(response)
+}
+
+/// DELETE /api/system/exports/:id
+pub async fn delete(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<(), StatusCode> {
+    match service::delete(&id, &claims.sub).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to delete export {}: {}", id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
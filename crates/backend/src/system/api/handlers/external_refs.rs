@@ -0,0 +1,95 @@
+//! Хендлеры внешних ссылок документов.
+
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::Json;
+use contracts::system::external_refs::{
+    CreateExternalRefRequest, ExternalRefDto, ExternalRefFields,
+};
+use serde::Deserialize;
+
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::external_refs::service;
+use crate::system::org_context::ActiveOrganization;
+
+#[derive(Debug, Deserialize)]
+pub struct DocumentQuery {
+    pub entity_type: String,
+    pub document_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    #[serde(default)]
+    pub q: String,
+}
+
+/// Ошибки проверки отдаются с текстом для пользователя.
+fn map_error(err: anyhow::Error) -> (StatusCode, String) {
+    let message = err.to_string();
+    if message.contains("not found") {
+        (StatusCode::NOT_FOUND, String::new())
+    } else if let Some(text) = message.strip_prefix("Invalid request: ") {
+        (StatusCode::BAD_REQUEST, text.to_string())
+    } else {
+        tracing::error!("External refs API error: {}", message);
+        (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+    }
+}
+
+/// GET /api/external-refs?entity_type=&document_id= — ссылки документа.
+pub async fn list_for_document(
+    active_org: ActiveOrganization,
+    Query(query): Query<DocumentQuery>,
+) -> Result<Json<Vec<ExternalRefDto>>, (StatusCode, String)> {
+    service::list_for_document(&active_org, &query.entity_type, &query.document_id)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// POST /api/external-refs — добавить ссылку к документу.
+pub async fn create(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+    Json(request): Json<CreateExternalRefRequest>,
+) -> Result<Json<ExternalRefDto>, (StatusCode, String)> {
+    service::create(&active_org, &request, &claims.username)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// PUT /api/external-refs/:id — изменить систему, ключ или URL.
+pub async fn update(
+    active_org: ActiveOrganization,
+    Path(id): Path<String>,
+    Json(fields): Json<ExternalRefFields>,
+) -> Result<Json<ExternalRefDto>, (StatusCode, String)> {
+    service::update(&active_org, &id, &fields)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// DELETE /api/external-refs/:id
+pub async fn delete(
+    active_org: ActiveOrganization,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    service::delete(&active_org, &id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(map_error)
+}
+
+/// GET /api/external-refs/search?q= — глобальный поиск по ключу, системе и URL.
+pub async fn search(
+    active_org: ActiveOrganization,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<Vec<ExternalRefDto>>, (StatusCode, String)> {
+    service::search(&active_org, &query.q)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
@@ -0,0 +1,22 @@
+//! Пробы живости и готовности для reverse proxy (без авторизации).
+
+use axum::{http::StatusCode, Json};
+use contracts::system::health::HealthReportDto;
+
+use crate::system::health;
+
+/// GET /healthz — отчёт по проверкам, всегда 200 (процесс жив).
+pub async fn healthz() -> Json<HealthReportDto> {
+    Json(health::report().await)
+}
+
+/// GET /readyz — 503, пока критичные проверки не пройдены.
+pub async fn readyz() -> (StatusCode, Json<HealthReportDto>) {
+    let report = health::report().await;
+    let status = if report.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
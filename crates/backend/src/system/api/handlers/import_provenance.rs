@@ -0,0 +1,32 @@
+use axum::{extract::Query, http::StatusCode, Json};
+use contracts::system::import_provenance::ImportProvenanceResponse;
+use serde::Deserialize;
+
+use crate::system::import_provenance::repository;
+
+#[derive(Debug, Deserialize)]
+pub struct ProvenanceQuery {
+    pub session_id: String,
+    /// Только пакеты одного агрегата (карточка документа)
+    pub aggregate_index: Option<String>,
+}
+
+/// Пакеты, полученные от API в сессии импорта: параметры, sha256 ответа, число строк.
+pub async fn get_by_session(
+    Query(query): Query<ProvenanceQuery>,
+) -> Result<Json<ImportProvenanceResponse>, StatusCode> {
+    let aggregate_index = query
+        .aggregate_index
+        .as_deref()
+        .filter(|value| !value.is_empty());
+    let batches = repository::list_by_session(&query.session_id, aggregate_index)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load import provenance: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(Json(ImportProvenanceResponse::new(
+        query.session_id,
+        batches,
+    )))
+}
@@ -0,0 +1,16 @@
+//! Хендлер просмотра журнала сервера (только админ).
+
+use axum::{extract::Query, http::StatusCode, Json};
+use contracts::system::log_viewer::{LogQuery, LogQueryResponse};
+
+use crate::system::log_viewer::service;
+
+/// GET /api/sys/server-log?level=&module=&q=&request_id=&limit= — хвост журнала с фильтрами.
+pub async fn query(
+    Query(query): Query<LogQuery>,
+) -> Result<Json<LogQueryResponse>, (StatusCode, String)> {
+    service::query(query).await.map(Json).map_err(|e| {
+        tracing::error!("Server log query failed: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+    })
+}
@@ -0,0 +1,60 @@
+use axum::{http::StatusCode, Json};
+use contracts::system::notifications::{
+    NotificationInboxDto, NotificationPreferencesDto, NotificationPreferencesUpdate,
+};
+
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::notifications::service;
+
+fn map_error(err: anyhow::Error) -> (StatusCode, String) {
+    let message = err.to_string();
+    if message.starts_with("Invalid") {
+        (StatusCode::BAD_REQUEST, message)
+    } else if message.contains("not found") {
+        (StatusCode::NOT_FOUND, message)
+    } else {
+        tracing::error!("Notifications API error: {}", message);
+        (StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+/// GET /api/system/notifications/preferences
+pub async fn get_preferences(
+    CurrentUser(claims): CurrentUser,
+) -> Result<Json<NotificationPreferencesDto>, (StatusCode, String)> {
+    service::preferences_for(&claims.sub)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// PUT /api/system/notifications/preferences
+pub async fn save_preferences(
+    CurrentUser(claims): CurrentUser,
+    Json(update): Json<NotificationPreferencesUpdate>,
+) -> Result<Json<NotificationPreferencesDto>, (StatusCode, String)> {
+    service::save_preferences(&claims.sub, update)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// GET /api/system/notifications — входящие уведомления текущего пользователя.
+pub async fn inbox(
+    CurrentUser(claims): CurrentUser,
+) -> Result<Json<NotificationInboxDto>, (StatusCode, String)> {
+    service::inbox(&claims.sub)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// POST /api/system/notifications/read — отметить все как прочитанные.
+pub async fn mark_all_read(
+    CurrentUser(claims): CurrentUser,
+) -> Result<StatusCode, (StatusCode, String)> {
+    service::mark_all_read(&claims.sub)
+        .await
+        .map(|_| StatusCode::OK)
+        .map_err(map_error)
+}
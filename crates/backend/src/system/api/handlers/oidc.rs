@@ -0,0 +1,123 @@
+//! Хендлеры входа через OIDC: публичные шаги authorization code flow
+//! и настройки провайдера (только админ).
+
+use axum::{
+    extract::Query,
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+    Json,
+};
+use contracts::system::auth::{LoginResponse, OidcExchangeRequest, OidcLoginInfo, OidcSettings};
+use serde::Deserialize;
+
+use crate::system::api::handlers::auth::login_response;
+use crate::system::auth::oidc;
+use crate::system::settings::service as settings_service;
+use crate::system::users::service as user_service;
+
+/// Возврат на фронтенд с параметром для страницы входа.
+async fn back_to_frontend(param: &str, value: &str) -> Response {
+    let base = settings_service::get_oidc_settings()
+        .await
+        .map(|s| oidc::post_login_url(&s))
+        .unwrap_or_else(|_| "/".to_string());
+    let separator = if base.contains('?') { '&' } else { '?' };
+    Redirect::to(&format!(
+        "{}{}{}={}",
+        base,
+        separator,
+        param,
+        urlencoding::encode(value)
+    ))
+    .into_response()
+}
+
+/// GET /api/system/auth/oidc — показывать ли кнопку входа через провайдера.
+pub async fn login_info() -> Json<OidcLoginInfo> {
+    let info = match oidc::enabled_settings().await {
+        Ok(settings) => OidcLoginInfo {
+            enabled: true,
+            display_name: settings.display_name,
+        },
+        Err(_) => OidcLoginInfo::default(),
+    };
+    Json(info)
+}
+
+/// GET /api/system/auth/oidc/start — перенаправление к провайдеру.
+pub async fn start() -> Response {
+    match oidc::authorization_url().await {
+        Ok(url) => Redirect::to(&url).into_response(),
+        Err(e) => {
+            tracing::error!("OIDC start failed: {}", e);
+            back_to_frontend("oidc_error", "Вход через SSO недоступен").await
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+/// GET /api/system/auth/oidc/callback — ответ провайдера.
+pub async fn callback(Query(query): Query<CallbackQuery>) -> Response {
+    if let Some(error) = query.error {
+        let message = query.error_description.unwrap_or(error);
+        tracing::warn!("OIDC provider returned error: {}", message);
+        return back_to_frontend("oidc_error", &message).await;
+    }
+    let (Some(code), Some(state)) = (query.code, query.state) else {
+        return back_to_frontend("oidc_error", "Некорректный ответ провайдера").await;
+    };
+    match oidc::complete_login(&code, &state).await {
+        Ok(login_code) => back_to_frontend("oidc_code", &login_code).await,
+        Err(e) => {
+            tracing::warn!("OIDC login failed: {:#}", e);
+            back_to_frontend("oidc_error", &e.to_string()).await
+        }
+    }
+}
+
+/// POST /api/system/auth/oidc/exchange — одноразовый код → токены.
+pub async fn exchange(
+    Json(request): Json<OidcExchangeRequest>,
+) -> Result<Json<LoginResponse>, StatusCode> {
+    let user_id = oidc::redeem_login_code(&request.code).ok_or(StatusCode::UNAUTHORIZED)?;
+    let user = user_service::get_by_id(&user_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .filter(|u| u.is_active)
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    login_response(user)
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// GET /api/sys/auth/oidc
+pub async fn get_settings() -> Result<Json<OidcSettings>, StatusCode> {
+    oidc::settings_for_admin().await.map(Json).map_err(|e| {
+        tracing::error!("Failed to load OIDC settings: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// PUT /api/sys/auth/oidc
+pub async fn save_settings(
+    Json(settings): Json<OidcSettings>,
+) -> Result<Json<OidcSettings>, (StatusCode, String)> {
+    oidc::save_settings(settings).await.map(Json).map_err(|e| {
+        let message = e.to_string();
+        if message.starts_with("Invalid") {
+            (StatusCode::BAD_REQUEST, message)
+        } else {
+            tracing::error!("Failed to save OIDC settings: {}", message);
+            (StatusCode::INTERNAL_SERVER_ERROR, message)
+        }
+    })
+}
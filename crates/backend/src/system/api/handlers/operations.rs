@@ -0,0 +1,66 @@
+//! Хендлеры пакетных операций: запуск в фоне и опрос статуса.
+
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::Json;
+use contracts::system::operations::{
+    OperationDto, OperationListResponse, OperationRequest, StartOperationResponse,
+};
+
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::operations::service;
+
+/// POST /api/operations — поставить операцию в очередь.
+pub async fn start(
+    CurrentUser(claims): CurrentUser,
+    Json(request): Json<OperationRequest>,
+) -> Result<Json<StartOperationResponse>, (StatusCode, String)> {
+    service::validate(&request).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let allowed = service::is_allowed(&request, &claims).await.map_err(|e| {
+        tracing::error!("Failed to check operation access: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+    })?;
+    if !allowed {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Недостаточно прав для этой операции".to_string(),
+        ));
+    }
+
+    let operation = service::start(request, &claims.sub).await.map_err(|e| {
+        tracing::error!("Failed to start operation: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+    })?;
+    Ok(Json(StartOperationResponse {
+        operation_id: operation.id,
+    }))
+}
+
+/// GET /api/operations — последние операции текущего пользователя.
+pub async fn list(
+    CurrentUser(claims): CurrentUser,
+) -> Result<Json<OperationListResponse>, StatusCode> {
+    service::list_for_user(&claims.sub)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("Failed to list operations: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// GET /api/operations/:id — статус, прогресс и итог операции.
+pub async fn get(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<OperationDto>, StatusCode> {
+    service::get(&id, &claims.sub)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get operation {}: {}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
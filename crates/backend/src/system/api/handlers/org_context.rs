@@ -0,0 +1,72 @@
+//! Активная организация текущего пользователя (переключатель в шапке).
+
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use contracts::system::org_context::{
+    ActiveOrganizationDto, ActiveOrganizationUpdateRequest, UserOrganizationsDto,
+};
+
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::org_context::service;
+use crate::system::quotas::service::CurrentOrganization;
+
+fn map_error(err: anyhow::Error) -> StatusCode {
+    let message = err.to_string();
+    if message.contains("Forbidden") {
+        StatusCode::FORBIDDEN
+    } else if message.contains("not found") {
+        StatusCode::NOT_FOUND
+    } else {
+        tracing::error!("Active organization API error: {}", message);
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// GET /api/system/active-organization
+pub async fn get(
+    CurrentUser(claims): CurrentUser,
+    host: Option<Extension<CurrentOrganization>>,
+) -> Result<Json<ActiveOrganizationDto>, StatusCode> {
+    let host = host.map(|Extension(CurrentOrganization(id))| id);
+    service::get(&claims.sub, claims.is_admin, host.as_deref())
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// PUT /api/system/active-organization — `organization_id: null` снимает ограничение
+/// (только администратор).
+pub async fn set(
+    CurrentUser(claims): CurrentUser,
+    host: Option<Extension<CurrentOrganization>>,
+    Json(req): Json<ActiveOrganizationUpdateRequest>,
+) -> Result<Json<ActiveOrganizationDto>, StatusCode> {
+    let host = host.map(|Extension(CurrentOrganization(id))| id);
+    service::set(&claims.sub, claims.is_admin, host.as_deref(), req)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// GET /api/system/users/:id/organizations — организации, доступные пользователю.
+pub async fn get_user_organizations(
+    Path(id): Path<String>,
+) -> Result<Json<UserOrganizationsDto>, StatusCode> {
+    service::get_memberships(&id)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// PUT /api/system/users/:id/organizations — заменяет список доступных организаций.
+pub async fn update_user_organizations(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+    Json(dto): Json<UserOrganizationsDto>,
+) -> Result<Json<UserOrganizationsDto>, StatusCode> {
+    service::set_memberships(&id, dto, &claims.sub)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
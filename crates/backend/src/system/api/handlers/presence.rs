@@ -0,0 +1,127 @@
+//! Присутствие на формах документов: SSE-поток снимков и отметки
+//! «редактирую» / «сохранил».
+
+use std::convert::Infallible;
+
+use axum::{
+    extract::Query,
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+    Json,
+};
+use contracts::system::presence::{PresenceSavedRequest, PresenceUpdateRequest};
+use futures_util::Stream;
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::auth::jwt;
+use crate::system::presence;
+
+const MAX_KEY_LEN: usize = 128;
+
+fn valid_doc(entity: &str, entity_id: &str) -> bool {
+    !entity.is_empty()
+        && !entity_id.is_empty()
+        && entity.len() <= MAX_KEY_LEN
+        && entity_id.len() <= MAX_KEY_LEN
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StreamQuery {
+    pub entity: String,
+    pub entity_id: String,
+    /// EventSource не умеет передавать заголовки, поэтому access-токен идёт в query
+    pub token: String,
+}
+
+/// GET /api/system/presence/stream?entity=&entity_id=&token=
+/// События `presence` со снимком `PresenceSnapshot`: сразу после подключения и при
+/// каждом изменении по документу. Пока соединение открыто, пользователь числится
+/// среди зрителей документа.
+pub async fn stream(
+    Query(query): Query<StreamQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
+    if !valid_doc(&query.entity, &query.entity_id) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let claims = jwt::validate_token(&query.token)
+        .await
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    struct StreamState {
+        entity: String,
+        entity_id: String,
+        connection: presence::Connection,
+        changes: tokio::sync::broadcast::Receiver<String>,
+        initial: bool,
+    }
+
+    let state = StreamState {
+        // Подписываемся до join, чтобы не пропустить изменения между ними
+        changes: presence::subscribe(),
+        connection: presence::join(
+            &query.entity,
+            &query.entity_id,
+            &claims.sub,
+            &claims.username,
+        ),
+        entity: query.entity,
+        entity_id: query.entity_id,
+        initial: true,
+    };
+
+    let stream = futures_util::stream::unfold(state, |mut st| async move {
+        if !st.initial {
+            loop {
+                match st.changes.recv().await {
+                    Ok(key) if key == st.connection.key() => break,
+                    Ok(_) => continue,
+                    // Пропустили часть изменений — просто отправим актуальный снимок
+                    Err(RecvError::Lagged(_)) => break,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }
+        st.initial = false;
+        let snapshot = presence::snapshot(&st.entity, &st.entity_id);
+        let payload = serde_json::to_string(&snapshot).unwrap_or_default();
+        Some((Ok(Event::default().event("presence").data(payload)), st))
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// POST /api/system/presence — есть ли у пользователя несохранённые изменения.
+pub async fn update(
+    CurrentUser(claims): CurrentUser,
+    Json(request): Json<PresenceUpdateRequest>,
+) -> StatusCode {
+    if !valid_doc(&request.entity, &request.entity_id) {
+        return StatusCode::BAD_REQUEST;
+    }
+    presence::set_editing(
+        &request.entity,
+        &request.entity_id,
+        &claims.sub,
+        request.editing,
+    );
+    StatusCode::OK
+}
+
+/// POST /api/system/presence/saved — документ сохранён текущим пользователем.
+pub async fn saved(
+    CurrentUser(claims): CurrentUser,
+    Json(request): Json<PresenceSavedRequest>,
+) -> StatusCode {
+    if !valid_doc(&request.entity, &request.entity_id) {
+        return StatusCode::BAD_REQUEST;
+    }
+    presence::record_saved(
+        &request.entity,
+        &request.entity_id,
+        &claims.sub,
+        &claims.username,
+    );
+    StatusCode::OK
+}
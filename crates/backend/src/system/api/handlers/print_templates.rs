@@ -0,0 +1,115 @@
+//! Хендлеры печатных форм: ведение шаблонов (админ) и печать документа по шаблону.
+
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    Json,
+};
+use contracts::system::auth::TokenClaims;
+use contracts::system::print_templates::{
+    PrintTemplateDto, PrintTemplateUpsertRequest, RenderedPrintDocumentDto,
+};
+use serde::Deserialize;
+
+use crate::system::access::resolver;
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::org_context::ActiveOrganization;
+use crate::system::print_templates::service;
+
+fn map_error(err: anyhow::Error) -> StatusCode {
+    let message = err.to_string();
+    if message.contains("not found") {
+        StatusCode::NOT_FOUND
+    } else if message.contains("Invalid") {
+        StatusCode::BAD_REQUEST
+    } else {
+        tracing::error!("Print templates API error: {}", message);
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// Печатать документы агрегата может тот, кто видит сами документы.
+async fn ensure_can_read(claims: &TokenClaims, entity_type: &str) -> Result<(), StatusCode> {
+    match resolver::can_read_scope(claims, entity_type).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(StatusCode::FORBIDDEN),
+        Err(e) => {
+            tracing::error!("Print templates: failed to resolve scopes: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/sys/print-templates — все шаблоны.
+pub async fn list_all() -> Result<Json<Vec<PrintTemplateDto>>, StatusCode> {
+    service::list(None).await.map(Json).map_err(map_error)
+}
+
+/// POST /api/sys/print-templates — создать шаблон.
+pub async fn create(
+    CurrentUser(claims): CurrentUser,
+    Json(req): Json<PrintTemplateUpsertRequest>,
+) -> Result<Json<PrintTemplateDto>, StatusCode> {
+    service::save(None, &req, &claims.username)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// PUT /api/sys/print-templates/:id — сохранить шаблон.
+pub async fn update(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+    Json(req): Json<PrintTemplateUpsertRequest>,
+) -> Result<Json<PrintTemplateDto>, StatusCode> {
+    service::save(Some(&id), &req, &claims.username)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// DELETE /api/sys/print-templates/:id
+pub async fn delete(Path(id): Path<String>) -> Result<StatusCode, StatusCode> {
+    service::delete(&id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(map_error)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ForEntityQuery {
+    pub entity_type: String,
+}
+
+/// GET /api/system/print-templates?entity_type=a012_wb_sales — шаблоны для меню
+/// «Печать» карточки документа.
+pub async fn list_for_entity(
+    CurrentUser(claims): CurrentUser,
+    Query(query): Query<ForEntityQuery>,
+) -> Result<Json<Vec<PrintTemplateDto>>, StatusCode> {
+    ensure_can_read(&claims, &query.entity_type).await?;
+    service::list(Some(&query.entity_type))
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// GET /api/system/print-templates/:id/render/:document_id — шаблон, заполненный
+/// данными документа. Печать видит то же, что карточка: документ активной
+/// организации и суммы только при доступе к финансам.
+pub async fn render(
+    CurrentUser(claims): CurrentUser,
+    active_org: ActiveOrganization,
+    Path((id, document_id)): Path<(String, String)>,
+) -> Result<Json<RenderedPrintDocumentDto>, StatusCode> {
+    let template = service::get(&id).await.map_err(map_error)?;
+    ensure_can_read(&claims, &template.entity_type).await?;
+    let show_finance = resolver::can_view_finance(&claims).await.map_err(|e| {
+        tracing::error!("Print templates: failed to resolve finance access: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    service::render(&template, &document_id, &active_org, show_finance)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
@@ -0,0 +1,32 @@
+//! Хендлеры архивации проекций по месяцам (страница «Архив проекций»).
+
+use axum::{http::StatusCode, Json};
+use contracts::system::projection_archive::{
+    ArchiveMoveRequest, ArchiveMoveResultDto, ArchiveStatusDto,
+};
+
+use crate::shared::data::projection_archive;
+
+/// GET /api/sys/projection-archive/status — граница и размеры месячных партиций.
+pub async fn get_status() -> Result<Json<ArchiveStatusDto>, StatusCode> {
+    projection_archive::status().await.map(Json).map_err(|e| {
+        tracing::error!("Failed to get projection archive status: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// POST /api/sys/projection-archive/move — перенести в архив строки раньше месяца.
+pub async fn move_to_archive(
+    Json(req): Json<ArchiveMoveRequest>,
+) -> Result<Json<ArchiveMoveResultDto>, StatusCode> {
+    if projection_archive::validate_month(&req.before_month).is_err() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    projection_archive::archive_before(&req.before_month)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("Failed to move projections to archive: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
@@ -0,0 +1,64 @@
+//! Хендлеры снимков итогов проекций и их сравнения (страница «Снимки проекций»).
+
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    Json,
+};
+use contracts::system::projection_snapshots::{
+    CreateProjectionSnapshotRequest, ProjectionSnapshotDto, SnapshotCompareQuery,
+    SnapshotCompareResponse,
+};
+
+use crate::shared::data::projection_snapshots;
+use crate::system::auth::extractor::CurrentUser;
+
+fn map_error(context: &str, err: anyhow::Error) -> (StatusCode, String) {
+    let message = err.to_string();
+    if message.contains("Invalid") {
+        (StatusCode::BAD_REQUEST, message)
+    } else {
+        tracing::error!("{}: {}", context, message);
+        (StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+/// GET /api/sys/projection-snapshots — снимки, новые первыми.
+pub async fn list() -> Result<Json<Vec<ProjectionSnapshotDto>>, (StatusCode, String)> {
+    projection_snapshots::list()
+        .await
+        .map(Json)
+        .map_err(|e| map_error("Failed to list projection snapshots", e))
+}
+
+/// POST /api/sys/projection-snapshots — зафиксировать итоги p900 за период.
+pub async fn create(
+    CurrentUser(claims): CurrentUser,
+    Json(req): Json<CreateProjectionSnapshotRequest>,
+) -> Result<Json<ProjectionSnapshotDto>, (StatusCode, String)> {
+    projection_snapshots::create(&req, &claims.username)
+        .await
+        .map(Json)
+        .map_err(|e| map_error("Failed to create projection snapshot", e))
+}
+
+/// DELETE /api/sys/projection-snapshots/:id
+pub async fn delete(Path(id): Path<String>) -> Result<StatusCode, (StatusCode, String)> {
+    match projection_snapshots::delete(&id).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err((StatusCode::NOT_FOUND, "Снимок не найден".to_string())),
+        Err(e) => Err(map_error("Failed to delete projection snapshot", e)),
+    }
+}
+
+/// GET /api/sys/projection-snapshots/compare?base=&target= — построчное сравнение
+/// снимка с другим снимком или с текущими данными (`target=current`).
+pub async fn compare(
+    Query(q): Query<SnapshotCompareQuery>,
+) -> Result<Json<SnapshotCompareResponse>, (StatusCode, String)> {
+    match projection_snapshots::compare(&q.base, &q.target, q.only_changed.unwrap_or(true)).await {
+        Ok(Some(result)) => Ok(Json(result)),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "Снимок не найден".to_string())),
+        Err(e) => Err(map_error("Failed to compare projection snapshots", e)),
+    }
+}
@@ -0,0 +1,36 @@
+//! Хендлеры квот организаций (только админ).
+
+use axum::{extract::Path, http::StatusCode, Json};
+use contracts::system::quotas::{OrgQuotaDto, OrgQuotaUpdateRequest};
+
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::quotas::service;
+
+fn map_error(err: anyhow::Error) -> StatusCode {
+    let message = err.to_string();
+    if message.contains("not found") {
+        StatusCode::NOT_FOUND
+    } else if message.contains("Invalid") {
+        StatusCode::BAD_REQUEST
+    } else {
+        tracing::error!("Quotas API error: {}", message);
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// GET /api/sys/quotas — квоты и использование всех организаций.
+pub async fn list() -> Result<Json<Vec<OrgQuotaDto>>, StatusCode> {
+    service::list().await.map(Json).map_err(map_error)
+}
+
+/// PUT /api/sys/quotas/:organization_id — задать лимиты организации.
+pub async fn update(
+    CurrentUser(claims): CurrentUser,
+    Path(organization_id): Path<String>,
+    Json(req): Json<OrgQuotaUpdateRequest>,
+) -> Result<StatusCode, StatusCode> {
+    service::update(&organization_id, req, &claims.username)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(map_error)
+}
@@ -0,0 +1,205 @@
+//! Хендлеры решений по физическим возвратам.
+
+use axum::body::Body;
+use axum::extract::{Multipart, Path, Query};
+use axum::http::{Response, StatusCode};
+use axum::Json;
+use contracts::shared::access::APPROVE_RETURN_DISPOSITION_SCOPE;
+use contracts::system::auth::TokenClaims;
+use contracts::system::return_dispositions::{
+    RejectReturnDispositionRequest, ReturnDispositionDto, ReturnDispositionView,
+    SaveReturnDispositionRequest,
+};
+use contracts::system::s3::S3FileDto;
+use serde::Deserialize;
+
+use super::s3::file_response;
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::operations::service::can_post;
+use crate::system::return_dispositions::items::is_return_document;
+use crate::system::return_dispositions::service::{self, DispositionError};
+use crate::system::s3::service::{self as s3, UploadedFile};
+
+#[derive(Debug, Deserialize)]
+pub struct DocumentQuery {
+    pub entity_type: String,
+    pub document_id: String,
+}
+
+fn map_error(err: DispositionError) -> (StatusCode, String) {
+    match err {
+        DispositionError::Invalid(message) => (StatusCode::BAD_REQUEST, message),
+        DispositionError::NotFound => (StatusCode::NOT_FOUND, err.to_string()),
+        DispositionError::Conflict(message) => (StatusCode::CONFLICT, message),
+        DispositionError::Internal(e) => {
+            tracing::error!("Return disposition operation failed: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
+
+fn internal(e: anyhow::Error) -> (StatusCode, String) {
+    map_error(DispositionError::Internal(e))
+}
+
+/// Решение оформляет тот, кто может проводить документ возврата.
+async fn require_edit_access(
+    entity_type: &str,
+    claims: &TokenClaims,
+) -> Result<(), (StatusCode, String)> {
+    if !can_post(entity_type, claims).await.map_err(internal)? {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Недостаточно прав для решения по возврату".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn can_approve(claims: &TokenClaims) -> anyhow::Result<bool> {
+    if claims.is_admin {
+        return Ok(true);
+    }
+    let scopes = crate::system::access::resolver::resolve_user_scopes(&claims.sub).await?;
+    Ok(scopes
+        .iter()
+        .any(|s| s.scope_id == APPROVE_RETURN_DISPOSITION_SCOPE))
+}
+
+async fn require_approve_access(claims: &TokenClaims) -> Result<(), (StatusCode, String)> {
+    if !can_approve(claims).await.map_err(internal)? {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Недостаточно прав для утверждения решений по возвратам".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// GET /api/return-dispositions?entity_type=&document_id= — решение, фото и движения.
+pub async fn get_for_document(
+    CurrentUser(claims): CurrentUser,
+    Query(query): Query<DocumentQuery>,
+) -> Result<Json<ReturnDispositionView>, (StatusCode, String)> {
+    service::validate_document(&query.entity_type, &query.document_id).map_err(map_error)?;
+    let can_approve = can_approve(&claims).await.map_err(internal)?;
+    service::get_view(&query.entity_type, &query.document_id, can_approve)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// POST /api/return-dispositions — создать решение или изменить черновик.
+pub async fn save(
+    CurrentUser(claims): CurrentUser,
+    Json(request): Json<SaveReturnDispositionRequest>,
+) -> Result<Json<ReturnDispositionDto>, (StatusCode, String)> {
+    service::validate_document(&request.entity_type, &request.document_id).map_err(map_error)?;
+    require_edit_access(&request.entity_type, &claims).await?;
+
+    service::save(&request, &claims.username)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// POST /api/return-dispositions/photos?entity_type=&document_id= — загрузить фото
+/// (multipart, поле `file`).
+pub async fn upload_photo(
+    CurrentUser(claims): CurrentUser,
+    Query(query): Query<DocumentQuery>,
+    mut multipart: Multipart,
+) -> Result<Json<S3FileDto>, (StatusCode, String)> {
+    service::validate_document(&query.entity_type, &query.document_id).map_err(map_error)?;
+    require_edit_access(&query.entity_type, &claims).await?;
+
+    let mut upload: Option<UploadedFile> = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let filename = field
+            .file_name()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| "photo".to_string());
+        let content_type = field.content_type().map(ToString::to_string);
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+        upload = Some(UploadedFile {
+            filename,
+            content_type,
+            bytes,
+        });
+    }
+    let upload = upload.ok_or((StatusCode::BAD_REQUEST, "Файл не передан".to_string()))?;
+
+    service::add_photo(&query.entity_type, &query.document_id, upload, &claims.sub)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// GET /api/return-dispositions/photos/:file_id — скачать фото документа возврата.
+pub async fn download_photo(Path(file_id): Path<String>) -> Result<Response<Body>, StatusCode> {
+    let download = s3::download(&file_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to download return photo {}: {}", file_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    // Только вложения документов возврата: остальные файлы S3 — через админку
+    if !download
+        .file
+        .document_type
+        .as_deref()
+        .is_some_and(is_return_document)
+    {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    file_response(download)
+}
+
+/// POST /api/return-dispositions/:id/submit — отправить на утверждение.
+pub async fn submit(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<ReturnDispositionDto>, (StatusCode, String)> {
+    let disposition = service::get(&id).await.map_err(map_error)?;
+    require_edit_access(&disposition.entity_type, &claims).await?;
+
+    service::submit(&id).await.map(Json).map_err(map_error)
+}
+
+/// POST /api/return-dispositions/:id/approve — утвердить и записать движения.
+pub async fn approve(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<ReturnDispositionDto>, (StatusCode, String)> {
+    require_approve_access(&claims).await?;
+
+    service::approve(&id, &claims.username)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
+
+/// POST /api/return-dispositions/:id/reject — отклонить с причиной.
+pub async fn reject(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+    Json(request): Json<RejectReturnDispositionRequest>,
+) -> Result<Json<ReturnDispositionDto>, (StatusCode, String)> {
+    require_approve_access(&claims).await?;
+
+    service::reject(&id, &claims.username, &request.reason)
+        .await
+        .map(Json)
+        .map_err(map_error)
+}
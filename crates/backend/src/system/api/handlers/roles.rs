@@ -2,7 +2,9 @@ use axum::{
     extract::{Json, Path},
     http::StatusCode,
 };
-use contracts::system::roles::{CreateRoleDto, Role, RoleScopeAccess, UpdateRoleDto};
+use contracts::system::roles::{
+    CreateRoleDto, Role, RolePreview, RoleScopeAccess, UpdateRoleDto, UserRolesDto,
+};
 
 use crate::system::roles::service;
 
@@ -71,6 +73,24 @@ pub async fn get_role_permissions(
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
 }
 
+/// GET /api/system/roles/:id/preview
+/// Effective scopes of a role for the admin "view as role" preview. Read-only.
+pub async fn preview_role(Path(id): Path<String>) -> Result<Json<RolePreview>, StatusCode> {
+    match service::preview(&id).await {
+        Ok(preview) => Ok(Json(preview)),
+        Err(e) => {
+            let msg = e.to_string();
+            if msg.contains("not found") {
+                Err(StatusCode::NOT_FOUND)
+            } else if msg.contains("admin role") {
+                Err(StatusCode::BAD_REQUEST)
+            } else {
+                Err(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
+    }
+}
+
 /// PUT /api/system/roles/:id/permissions
 /// Replace all scope grants for a custom role.
 pub async fn update_role_permissions(
@@ -89,3 +109,31 @@ pub async fn update_role_permissions(
         }
     }
 }
+
+/// GET /api/system/users/:id/roles
+pub async fn get_user_roles(Path(id): Path<String>) -> Result<Json<UserRolesDto>, StatusCode> {
+    match service::get_user_roles(&id).await {
+        Ok(dto) => Ok(Json(dto)),
+        Err(e) if e.to_string().contains("not found") => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// PUT /api/system/users/:id/roles
+/// Replace all additional roles of a user.
+pub async fn update_user_roles(
+    Path(id): Path<String>,
+    Json(dto): Json<UserRolesDto>,
+) -> Result<StatusCode, StatusCode> {
+    match service::update_user_roles(&id, dto).await {
+        Ok(_) => Ok(StatusCode::OK),
+        Err(e) => {
+            let msg = e.to_string();
+            if msg.contains("system role") || msg.contains("not found") {
+                Err(StatusCode::BAD_REQUEST)
+            } else {
+                Err(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
+    }
+}
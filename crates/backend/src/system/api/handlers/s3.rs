@@ -3,11 +3,14 @@ use axum::extract::{Multipart, Path, Query};
 use axum::http::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
 use axum::http::{HeaderValue, Response, StatusCode};
 use axum::Json;
-use contracts::system::s3::{S3FileCategory, S3FileListResponse, S3UploadResponse};
+use contracts::system::s3::{
+    S3FileCategory, S3FileListResponse, S3TextSearchResponse, S3UploadResponse,
+};
 use serde::Deserialize;
 
 use crate::system::auth::extractor::CurrentUser;
-use crate::system::s3::service::{self, UploadedFile};
+use crate::system::s3::service::{self, DownloadedFile, UploadedFile};
+use crate::system::s3::text_index;
 
 #[derive(Debug, Deserialize)]
 pub struct ListQuery {
@@ -52,6 +55,8 @@ pub async fn upload_file(
     mut multipart: Multipart,
 ) -> Result<Json<S3UploadResponse>, StatusCode> {
     let mut category = S3FileCategory::Documents;
+    let mut document_type: Option<String> = None;
+    let mut document_id: Option<String> = None;
     let mut upload: Option<UploadedFile> = None;
 
     while let Some(field) = multipart.next_field().await.map_err(|err| {
@@ -65,6 +70,16 @@ pub async fn upload_file(
             continue;
         }
 
+        if name == "document_type" || name == "document_id" {
+            let value = field.text().await.map_err(|_| StatusCode::BAD_REQUEST)?;
+            if name == "document_type" {
+                document_type = Some(value);
+            } else {
+                document_id = Some(value);
+            }
+            continue;
+        }
+
         if name == "file" {
             let filename = field
                 .file_name()
@@ -81,17 +96,57 @@ pub async fn upload_file(
     }
 
     let upload = upload.ok_or(StatusCode::BAD_REQUEST)?;
-    let file = service::upload(category, upload, Some(claims.sub))
+    let mut file = service::upload(category, upload, Some(claims.sub))
         .await
         .map_err(map_error)?;
+
+    // Привязка к документу: поиск по тексту вложения находит документ
+    if document_type.is_some() || document_id.is_some() {
+        service::attach_to_document(&file.id, document_type.as_deref(), document_id.as_deref())
+            .await
+            .map_err(|err| {
+                if err.to_string().contains("Invalid") {
+                    StatusCode::BAD_REQUEST
+                } else {
+                    map_error(err)
+                }
+            })?;
+        file.document_type = document_type.filter(|v| !v.trim().is_empty());
+        file.document_id = document_id.filter(|v| !v.trim().is_empty());
+    }
     Ok(Json(S3UploadResponse { file }))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TextSearchQuery {
+    pub q: String,
+    pub limit: Option<u64>,
+}
+
+/// GET /api/sys/s3/search — поиск по распознанному тексту вложений.
+pub async fn search_text(
+    Query(query): Query<TextSearchQuery>,
+) -> Result<Json<S3TextSearchResponse>, StatusCode> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let items = text_index::search(&query.q, limit)
+        .await
+        .map_err(map_error)?;
+    let pending_files = text_index::pending_count().await.map_err(map_error)?;
+    Ok(Json(S3TextSearchResponse {
+        items,
+        pending_files,
+    }))
+}
+
 pub async fn download_file(Path(id): Path<String>) -> Result<Response<Body>, StatusCode> {
     let Some(download) = service::download(&id).await.map_err(map_error)? else {
         return Err(StatusCode::NOT_FOUND);
     };
+    file_response(download)
+}
 
+/// Ответ со скачиваемым файлом (тип содержимого и имя для сохранения).
+pub(crate) fn file_response(download: DownloadedFile) -> Result<Response<Body>, StatusCode> {
     let filename = service::sanitize_filename(&download.file.original_filename);
     let mut response = Response::new(Body::from(download.bytes));
     let headers = response.headers_mut();
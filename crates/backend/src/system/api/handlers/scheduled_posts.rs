@@ -0,0 +1,94 @@
+//! Хендлеры отложенного проведения документов.
+
+use axum::extract::{Path, Query};
+use axum::http::StatusCode;
+use axum::Json;
+use chrono::Utc;
+use contracts::system::auth::TokenClaims;
+use contracts::system::scheduled_posts::{SchedulePostRequest, ScheduledPostDto};
+use serde::Deserialize;
+
+use crate::system::auth::extractor::CurrentUser;
+use crate::system::operations::service::can_post;
+use crate::system::scheduled_posts::service;
+
+#[derive(Debug, Deserialize)]
+pub struct DocumentQuery {
+    pub entity_type: String,
+    pub document_id: String,
+}
+
+async fn require_post_access(
+    entity_type: &str,
+    claims: &TokenClaims,
+) -> Result<(), (StatusCode, String)> {
+    let allowed = can_post(entity_type, claims).await.map_err(|e| {
+        tracing::error!("Failed to check posting access: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+    })?;
+    if !allowed {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Недостаточно прав для проведения документа".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// POST /api/scheduled-posts — поставить документ в очередь на проведение.
+pub async fn schedule(
+    CurrentUser(claims): CurrentUser,
+    Json(request): Json<SchedulePostRequest>,
+) -> Result<Json<ScheduledPostDto>, (StatusCode, String)> {
+    let post_at = service::validate(&request, Utc::now())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    require_post_access(&request.entity_type, &claims).await?;
+
+    service::schedule(&request, post_at, &claims.username)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("Failed to schedule posting: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        })
+}
+
+/// GET /api/scheduled-posts?entity_type=&document_id= — ожидающее задание документа.
+pub async fn get_for_document(
+    Query(query): Query<DocumentQuery>,
+) -> Result<Json<Option<ScheduledPostDto>>, StatusCode> {
+    service::get_pending(&query.entity_type, &query.document_id)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("Failed to get scheduled posting: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// DELETE /api/scheduled-posts/:id — отменить ожидающее задание.
+pub async fn cancel(
+    CurrentUser(claims): CurrentUser,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let job = service::get(&id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get scheduled posting {}: {}", id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        })?
+        .ok_or((StatusCode::NOT_FOUND, String::new()))?;
+    require_post_access(&job.entity_type, &claims).await?;
+
+    let cancelled = service::cancel(&id).await.map_err(|e| {
+        tracing::error!("Failed to cancel scheduled posting {}: {}", id, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+    })?;
+    if !cancelled {
+        return Err((
+            StatusCode::CONFLICT,
+            "Задание уже выполнено или отменено".to_string(),
+        ));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,11 +1,15 @@
+use crate::shared::error::AppError;
+use crate::system::auth::extractor::CurrentUser;
 use crate::system::tasks::abort_registry;
 use crate::system::tasks::change_token;
 use crate::system::tasks::logger::get_global_task_logger;
 use crate::system::tasks::registry::get_global_registry;
 use crate::system::tasks::resource_coordinator::get_global_resource_coordinator;
 use crate::system::tasks::runs_service;
+use crate::system::tasks::schedule;
 use crate::system::tasks::service;
 use crate::system::tasks::task_session_runner::{spawn_task_session, TaskSessionParams};
+use anyhow::Context;
 use axum::{extract::Path, extract::Query, response::IntoResponse, Json};
 use chrono::Utc;
 use contracts::domain::common::AggregateId;
@@ -13,14 +17,17 @@ use contracts::system::tasks::aggregate::ScheduledTaskId;
 use contracts::system::tasks::history::{
     TaskHistoryMetric, TaskHistoryRequest, TaskHistoryResponse, TaskHistoryScale,
 };
-use contracts::system::tasks::metadata::TaskMetadataDto;
+use contracts::system::tasks::metadata::{TaskConcurrencyClass, TaskMetadataDto};
 use contracts::system::tasks::progress::{TaskProgressResponse, TaskStatus};
 use contracts::system::tasks::request::{
-    CreateScheduledTaskDto, SchedulerStatusDto, SetWatermarkDto, ToggleScheduledTaskEnabledDto,
-    UpdateScheduledTaskDto,
+    CreateScheduledTaskDto, ImportSummarySubscriptionDto, SchedulerStatusDto, SetWatermarkDto,
+    ToggleScheduledTaskEnabledDto, UpdateScheduledTaskDto,
+};
+use contracts::system::tasks::response::{
+    ScheduleCronPreviewResponse, ScheduledTaskListResponse, ScheduledTaskResponse,
 };
-use contracts::system::tasks::response::{ScheduledTaskListResponse, ScheduledTaskResponse};
 use contracts::system::tasks::runs::{
+    ConcurrencyClassStatus, ConcurrencySlotItem, ConcurrencyStatusResponse,
     LiveMemoryProgressResponse, RecentRunsResponse, RunTaskResponse, TaskRunListResponse,
     TaskStartConflict,
 };
@@ -61,38 +68,55 @@ pub async fn get_scheduled_task(
 
 /// POST /api/sys/tasks
 pub async fn create_scheduled_task(
-    Json(dto): Json<CreateScheduledTaskDto>,
-) -> Result<Json<ScheduledTaskResponse>, axum::http::StatusCode> {
-    match service::create(dto).await {
-        Ok(task_id) => match service::get_by_id(&task_id).await {
-            Ok(Some(task)) => Ok(Json(task.into())),
-            _ => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
-        },
-        Err(e) => {
-            tracing::error!("Failed to create scheduled task: {}", e);
-            Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    }
+    Json(mut dto): Json<CreateScheduledTaskDto>,
+) -> Result<Json<ScheduledTaskResponse>, AppError> {
+    dto.schedule_cron =
+        schedule::validate(dto.schedule_cron.as_deref()).map_err(AppError::bad_request)?;
+    let task_id = service::create(dto)
+        .await
+        .context("Failed to create scheduled task")?;
+    let task = service::get_by_id(&task_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("Scheduled task not found"))?;
+    Ok(Json(task.into()))
 }
 
 /// PUT /api/sys/tasks/:id
 pub async fn update_scheduled_task(
     Path(id): Path<String>,
-    Json(dto): Json<UpdateScheduledTaskDto>,
-) -> Result<Json<ScheduledTaskResponse>, axum::http::StatusCode> {
-    let task_id =
-        ScheduledTaskId::from_string(&id).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    Json(mut dto): Json<UpdateScheduledTaskDto>,
+) -> Result<Json<ScheduledTaskResponse>, AppError> {
+    let task_id = ScheduledTaskId::from_string(&id).map_err(AppError::bad_request)?;
+    dto.schedule_cron =
+        schedule::validate(dto.schedule_cron.as_deref()).map_err(AppError::bad_request)?;
+    service::update(&task_id, dto)
+        .await
+        .with_context(|| format!("Failed to update scheduled task {}", id))?;
+    let task = service::get_by_id(&task_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("Scheduled task not found"))?;
+    Ok(Json(task.into()))
+}
 
-    match service::update(&task_id, dto).await {
-        Ok(_) => match service::get_by_id(&task_id).await {
-            Ok(Some(task)) => Ok(Json(task.into())),
-            _ => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
-        },
-        Err(e) => {
-            tracing::error!("Failed to update scheduled task {}: {}", id, e);
-            Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
-        }
-    }
+#[derive(Debug, Deserialize)]
+pub struct SchedulePreviewQuery {
+    #[serde(default)]
+    pub cron: String,
+}
+
+/// GET /api/sys/tasks/schedule-preview?cron=... — проверка выражения и ближайшие
+/// запуски для редактора расписания.
+pub async fn preview_schedule(
+    Query(query): Query<SchedulePreviewQuery>,
+) -> Result<Json<ScheduleCronPreviewResponse>, AppError> {
+    let schedule_cron = schedule::validate(Some(&query.cron))
+        .map_err(AppError::bad_request)?
+        .ok_or_else(|| AppError::bad_request("Расписание не задано"))?;
+    let next_runs = schedule::upcoming_runs(&schedule_cron, Utc::now(), schedule::PREVIEW_RUNS);
+    Ok(Json(ScheduleCronPreviewResponse {
+        schedule_cron,
+        next_runs,
+    }))
 }
 
 /// DELETE /api/sys/tasks/:id
@@ -176,6 +200,7 @@ pub async fn run_task_now(
         &task_label,
         &session_id,
         manager.metadata().write_tables,
+        manager.metadata().concurrency_class,
     ) {
         Ok(guard) => guard,
         Err(conflict) => {
@@ -224,6 +249,7 @@ pub async fn run_task_now(
         recompute_next_run_if_elapsed: false,
         logger,
         registry,
+        send_run_summary: false,
         resource_guard,
     });
 
@@ -277,6 +303,7 @@ pub async fn get_task_progress(
                         http_request_count: None,
                         http_bytes_sent: None,
                         http_bytes_received: None,
+                        http_retry_count: None,
                     })),
                     Err(e) => {
                         tracing::warn!(
@@ -310,6 +337,7 @@ pub async fn get_task_progress(
                 http_request_count: None,
                 http_bytes_sent: None,
                 http_bytes_received: None,
+                http_retry_count: None,
             })),
             Err(_) => Err(axum::http::StatusCode::NOT_IMPLEMENTED),
         },
@@ -421,6 +449,31 @@ pub async fn list_active_runs_with_progress(
     Ok(Json(LiveMemoryProgressResponse { items }))
 }
 
+/// GET /api/sys/tasks/runs/active/slots — занятые слоты классов параллельности
+/// (только память координатора, как и `runs/active/progress`).
+pub async fn list_concurrency_slots() -> Json<ConcurrencyStatusResponse> {
+    let mut running = get_global_resource_coordinator().running_by_class();
+    let classes = TaskConcurrencyClass::ALL
+        .into_iter()
+        .map(|class| ConcurrencyClassStatus {
+            class,
+            label: class.label().to_string(),
+            limit: class.max_concurrent(),
+            running: running
+                .remove(&class)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|slot| ConcurrencySlotItem {
+                    session_id: slot.session_id,
+                    task_label: slot.task,
+                    started_at: slot.acquired_at,
+                })
+                .collect(),
+        })
+        .collect();
+    Json(ConcurrencyStatusResponse { classes })
+}
+
 /// GET /api/sys/tasks/task_types — метаданные всех зарегистрированных типов задач
 pub async fn list_task_types() -> Result<Json<Vec<TaskMetadataDto>>, axum::http::StatusCode> {
     match get_global_registry() {
@@ -564,3 +617,53 @@ pub async fn set_scheduler_status(
         }
     }
 }
+
+/// Текущая подписка администратора и адрес, на который уйдёт сводка.
+async fn import_summary_subscription(
+    user_id: &str,
+) -> anyhow::Result<ImportSummarySubscriptionDto> {
+    let subscribers = crate::system::settings::service::get_import_summary_subscribers().await?;
+    let email = crate::system::users::repository::get_by_id(user_id)
+        .await?
+        .and_then(|user| user.email)
+        .filter(|email| !email.trim().is_empty());
+    Ok(ImportSummarySubscriptionDto {
+        subscribed: subscribers.iter().any(|id| id == user_id),
+        email,
+    })
+}
+
+/// GET /api/sys/tasks/import-summary/subscription
+pub async fn get_import_summary_subscription(
+    CurrentUser(claims): CurrentUser,
+) -> Result<Json<ImportSummarySubscriptionDto>, axum::http::StatusCode> {
+    import_summary_subscription(&claims.sub)
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("Failed to get import summary subscription: {}", e);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// POST /api/sys/tasks/import-summary/subscription
+pub async fn set_import_summary_subscription(
+    CurrentUser(claims): CurrentUser,
+    Json(dto): Json<ImportSummarySubscriptionDto>,
+) -> Result<Json<ImportSummarySubscriptionDto>, axum::http::StatusCode> {
+    let result = async {
+        let mut subscribers =
+            crate::system::settings::service::get_import_summary_subscribers().await?;
+        subscribers.retain(|id| id != &claims.sub);
+        if dto.subscribed {
+            subscribers.push(claims.sub.clone());
+        }
+        crate::system::settings::service::set_import_summary_subscribers(&subscribers).await?;
+        import_summary_subscription(&claims.sub).await
+    }
+    .await;
+    result.map(Json).map_err(|e| {
+        tracing::error!("Failed to set import summary subscription: {}", e);
+        axum::http::StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
@@ -1,8 +1,12 @@
 use axum::{
-    extract::{Json, Path},
+    extract::{Json, Path, Query},
     http::StatusCode,
 };
-use contracts::system::users::{ChangePasswordDto, CreateUserDto, UpdateUserDto, User};
+use chrono::Utc;
+use contracts::system::users::{
+    ChangePasswordDto, CreateUserDto, ExpiringUserDto, UpdateUserDto, User,
+};
+use serde::Deserialize;
 
 use crate::system::auth::extractor::CurrentUser;
 use crate::system::users::service;
@@ -16,6 +20,25 @@ pub async fn list(CurrentUser(_claims): CurrentUser) -> Result<Json<Vec<User>>,
     Ok(Json(users))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ExpiringQuery {
+    /// Горизонт отчёта в днях
+    pub days: Option<i64>,
+}
+
+/// Учётные записи с истекающим или истёкшим сроком доступа (admin only)
+pub async fn expiring(
+    CurrentUser(_claims): CurrentUser,
+    Query(query): Query<ExpiringQuery>,
+) -> Result<Json<Vec<ExpiringUserDto>>, StatusCode> {
+    let days = query.days.unwrap_or(30).clamp(0, 365);
+    let users = service::list_expiring(days, Utc::now().date_naive())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(users))
+}
+
 /// Get user by ID (admin only)
 pub async fn get_by_id(
     CurrentUser(_claims): CurrentUser,
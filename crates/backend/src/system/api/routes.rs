@@ -14,18 +14,211 @@ pub fn configure_system_routes() -> Router {
         // HEALTH CHECK
         // ========================================
         .route("/health", get(|| async { "ok" }))
+        .route("/healthz", get(handlers::health::healthz))
+        .route("/readyz", get(handlers::health::readyz))
         // ========================================
         // SYSTEM AUTH ROUTES (PUBLIC)
         // ========================================
         .route("/api/system/auth/login", post(handlers::auth::login))
         .route("/api/system/auth/refresh", post(handlers::auth::refresh))
         .route("/api/system/auth/logout", post(handlers::auth::logout))
+        // Вход через корпоративный OIDC-провайдер
+        .route("/api/system/auth/oidc", get(handlers::oidc::login_info))
+        .route("/api/system/auth/oidc/start", get(handlers::oidc::start))
+        .route(
+            "/api/system/auth/oidc/callback",
+            get(handlers::oidc::callback),
+        )
+        .route(
+            "/api/system/auth/oidc/exchange",
+            post(handlers::oidc::exchange),
+        )
+        // Брендирование для хоста запроса: нужно странице входа до авторизации
+        .route("/api/system/branding", get(handlers::branding::get_current))
+        // Режим окружения для баннера: тоже нужен до авторизации
+        .route(
+            "/api/system/environment",
+            get(handlers::environment::get_info),
+        )
         // System auth routes (protected)
         .route(
             "/api/system/auth/me",
             get(handlers::auth::current_user)
                 .layer(middleware::from_fn(auth::middleware::require_auth)),
         )
+        // User notifications (inbox + preferences)
+        .route(
+            "/api/system/notifications",
+            get(handlers::notifications::inbox)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/system/notifications/read",
+            post(handlers::notifications::mark_all_read)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/system/notifications/preferences",
+            get(handlers::notifications::get_preferences)
+                .put(handlers::notifications::save_preferences)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // "What's new" announcements feed of the current user
+        .route(
+            "/api/system/announcements",
+            get(handlers::announcements::feed)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/system/announcements/read",
+            post(handlers::announcements::mark_all_read)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // Data freshness badges: last import per source vs SLA
+        .route(
+            "/api/system/data-freshness",
+            get(handlers::data_freshness::list)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // Document history tab: who created/edited/posted/unposted a document
+        .route(
+            "/api/system/document-audit/:entity/:id",
+            get(handlers::document_audit::list)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // Print forms of a document card (claim letters, acceptance acts)
+        .route(
+            "/api/system/print-templates",
+            get(handlers::print_templates::list_for_entity)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/system/print-templates/:id/render/:document_id",
+            get(handlers::print_templates::render)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // My exports (background export jobs of the current user)
+        .route(
+            "/api/system/exports",
+            get(handlers::exports::list).layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/system/exports/:id/download",
+            get(handlers::exports::download)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/system/exports/:id",
+            axum::routing::delete(handlers::exports::delete)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // Export profiles (saved filter + columns + format, run on demand or by cron)
+        .route(
+            "/api/system/export-profiles",
+            get(handlers::export_profiles::list)
+                .post(handlers::export_profiles::create)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/system/export-profiles/:id",
+            axum::routing::put(handlers::export_profiles::update)
+                .delete(handlers::export_profiles::delete)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/system/export-profiles/:id/run",
+            post(handlers::export_profiles::run)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // Batch operations (background execution, status polling)
+        .route(
+            "/api/operations",
+            get(handlers::operations::list)
+                .post(handlers::operations::start)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/operations/:id",
+            get(handlers::operations::get)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // Deferred posting of documents (scheduled post jobs)
+        .route(
+            "/api/scheduled-posts",
+            get(handlers::scheduled_posts::get_for_document)
+                .post(handlers::scheduled_posts::schedule)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/scheduled-posts/:id",
+            axum::routing::delete(handlers::scheduled_posts::cancel)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // External references of documents (WB claim number, ERP document number, ...)
+        .route(
+            "/api/external-refs",
+            get(handlers::external_refs::list_for_document)
+                .post(handlers::external_refs::create)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/external-refs/search",
+            get(handlers::external_refs::search)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/external-refs/:id",
+            put(handlers::external_refs::update)
+                .delete(handlers::external_refs::delete)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // Return disposition workflow (decision, photos, approval, p917 ledger)
+        .route(
+            "/api/return-dispositions",
+            get(handlers::return_dispositions::get_for_document)
+                .post(handlers::return_dispositions::save)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/return-dispositions/photos",
+            post(handlers::return_dispositions::upload_photo)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/return-dispositions/photos/:file_id",
+            get(handlers::return_dispositions::download_photo)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/return-dispositions/:id/submit",
+            post(handlers::return_dispositions::submit)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/return-dispositions/:id/approve",
+            post(handlers::return_dispositions::approve)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/return-dispositions/:id/reject",
+            post(handlers::return_dispositions::reject)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // Presence on document forms (stream validates the token from query itself)
+        .route(
+            "/api/system/presence/stream",
+            get(handlers::presence::stream),
+        )
+        .route(
+            "/api/system/presence",
+            post(handlers::presence::update)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        .route(
+            "/api/system/presence/saved",
+            post(handlers::presence::saved)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
         // ========================================
         // SYSTEM USERS MANAGEMENT (admin only)
         // ========================================
@@ -35,6 +228,11 @@ pub fn configure_system_routes() -> Router {
                 .post(handlers::users::create)
                 .layer(middleware::from_fn(auth::middleware::require_admin)),
         )
+        .route(
+            "/api/system/users/expiring",
+            get(handlers::users::expiring)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
         .route(
             "/api/system/users/:id",
             get(handlers::users::get_by_id)
@@ -42,6 +240,18 @@ pub fn configure_system_routes() -> Router {
                 .delete(handlers::users::delete)
                 .layer(middleware::from_fn(auth::middleware::require_admin)),
         )
+        .route(
+            "/api/system/users/:id/roles",
+            get(handlers::roles::get_user_roles)
+                .put(handlers::roles::update_user_roles)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/system/users/:id/organizations",
+            get(handlers::org_context::get_user_organizations)
+                .put(handlers::org_context::update_user_organizations)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
         .route(
             "/api/system/users/:id/change-password",
             post(handlers::users::change_password)
@@ -62,6 +272,11 @@ pub fn configure_system_routes() -> Router {
                 .delete(handlers::roles::delete_role)
                 .layer(middleware::from_fn(auth::middleware::require_admin)),
         )
+        .route(
+            "/api/system/roles/:id/preview",
+            get(handlers::roles::preview_role)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
         .route(
             "/api/system/roles/:id/permissions",
             get(handlers::roles::get_role_permissions)
@@ -112,6 +327,15 @@ pub fn configure_system_routes() -> Router {
                 .layer(middleware::from_fn(auth::middleware::require_auth)),
         )
         // ========================================
+        // ACTIVE ORGANIZATION (header switcher; scopes document lists and details)
+        // ========================================
+        .route(
+            "/api/system/active-organization",
+            get(handlers::org_context::get)
+                .put(handlers::org_context::set)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // ========================================
         // SYSTEM PAGE HISTORY ("История открытых страниц")
         // ========================================
         .route(
@@ -141,6 +365,36 @@ pub fn configure_system_routes() -> Router {
                 .layer(middleware::from_fn(auth::middleware::require_admin)),
         )
         // ========================================
+        // BULK OPERATIONS HISTORY / UNDO (sys_bulk_operations)
+        // admin-only: отмена перепроводит документы разных агрегатов
+        // ========================================
+        .route(
+            "/api/sys/bulk-operations",
+            get(handlers::bulk_ops::list)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/bulk-operations/undo-window",
+            get(handlers::bulk_ops::get_undo_window)
+                .post(handlers::bulk_ops::set_undo_window)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/bulk-operations/:id/undo",
+            post(handlers::bulk_ops::undo)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/bulk-operations/lookup",
+            post(handlers::bulk_ops::lookup)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/bulk-operations/reassign",
+            post(handlers::bulk_ops::reassign)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        // ========================================
         // SYSTEM TASKS (sys_tasks) ROUTES
         // ========================================
         .route(
@@ -154,6 +408,11 @@ pub fn configure_system_routes() -> Router {
             get(handlers::tasks::list_task_types)
                 .layer(middleware::from_fn(auth::middleware::require_auth)),
         )
+        .route(
+            "/api/sys/tasks/schedule-preview",
+            get(handlers::tasks::preview_schedule)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
         .route(
             "/api/sys/tasks/runs/recent",
             get(handlers::tasks::list_recent_runs)
@@ -169,11 +428,22 @@ pub fn configure_system_routes() -> Router {
             get(handlers::tasks::list_active_runs_with_progress)
                 .layer(middleware::from_fn(auth::middleware::require_auth)),
         )
+        .route(
+            "/api/sys/tasks/runs/active/slots",
+            get(handlers::tasks::list_concurrency_slots)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
         .route(
             "/api/sys/tasks/runs/active",
             get(handlers::tasks::list_active_runs)
                 .layer(middleware::from_fn(auth::middleware::require_auth)),
         )
+        .route(
+            "/api/sys/tasks/import-summary/subscription",
+            get(handlers::tasks::get_import_summary_subscription)
+                .post(handlers::tasks::set_import_summary_subscription)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
         .route(
             "/api/sys/tasks/:id",
             get(handlers::tasks::get_scheduled_task)
@@ -228,6 +498,14 @@ pub fn configure_system_routes() -> Router {
                 .layer(middleware::from_fn(auth::middleware::require_admin)),
         )
         // ========================================
+        // IMPORT PROVENANCE
+        // ========================================
+        .route(
+            "/api/sys/import-provenance",
+            get(handlers::import_provenance::get_by_session)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // ========================================
         // RAW JSON DEBUG STORAGE
         // ========================================
         .route(
@@ -248,6 +526,9 @@ pub fn configure_system_routes() -> Router {
         .route(
             "/api/sys/raw-storage/cleanup",
             post(handlers::raw_storage::cleanup)
+                .layer(middleware::from_fn(
+                    crate::system::environment::require_confirmation,
+                ))
                 .layer(middleware::from_fn(auth::middleware::require_admin)),
         )
         .route(
@@ -262,6 +543,172 @@ pub fn configure_system_routes() -> Router {
                 .layer(middleware::from_fn(auth::middleware::require_admin)),
         )
         // ========================================
+        // BULK IMPORT (NDJSON история p903)
+        // ========================================
+        .route(
+            "/api/sys/bulk-import/p903",
+            post(handlers::bulk_import::import_p903)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        // ========================================
+        // PROJECTION ARCHIVE (p900/p904 *_archive)
+        // ========================================
+        .route(
+            "/api/sys/projection-archive/status",
+            get(handlers::projection_archive::get_status)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/projection-archive/move",
+            post(handlers::projection_archive::move_to_archive)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        // ========================================
+        // PROJECTION SNAPSHOTS (sys_projection_snapshots)
+        // ========================================
+        .route(
+            "/api/sys/projection-snapshots",
+            get(handlers::projection_snapshots::list)
+                .post(handlers::projection_snapshots::create)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/projection-snapshots/compare",
+            get(handlers::projection_snapshots::compare)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/projection-snapshots/:id",
+            axum::routing::delete(handlers::projection_snapshots::delete)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        // ========================================
+        // ORGANIZATION BRANDING (admin only)
+        // ========================================
+        .route(
+            "/api/sys/branding",
+            get(handlers::branding::list)
+                .put(handlers::branding::upsert)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/branding/:organization_id",
+            axum::routing::delete(handlers::branding::delete)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        // ========================================
+        // ANNOUNCEMENTS (admin only)
+        // ========================================
+        .route(
+            "/api/sys/announcements",
+            get(handlers::announcements::list)
+                .post(handlers::announcements::create)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/announcements/:id",
+            put(handlers::announcements::update)
+                .delete(handlers::announcements::delete)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        // ========================================
+        // DATA FRESHNESS SLA (admin only)
+        // ========================================
+        .route(
+            "/api/sys/data-freshness/sla",
+            get(handlers::data_freshness::get_sla)
+                .put(handlers::data_freshness::save_sla)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        // ========================================
+        // ORGANIZATION QUOTAS (admin only)
+        // ========================================
+        .route(
+            "/api/sys/quotas",
+            get(handlers::quotas::list).layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/quotas/:organization_id",
+            axum::routing::put(handlers::quotas::update)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        // ========================================
+        // ENVIRONMENT MODE: подтверждение опасных операций
+        // ========================================
+        .route(
+            "/api/sys/environment/confirm",
+            post(handlers::environment::confirm)
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
+        )
+        // ========================================
+        // DESCRIPTION TEMPLATES (admin only)
+        // ========================================
+        .route(
+            "/api/sys/description-templates",
+            get(handlers::description_templates::list)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/description-templates/:entity_type",
+            axum::routing::put(handlers::description_templates::upsert)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/description-templates/:entity_type",
+            axum::routing::delete(handlers::description_templates::delete)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/description-templates/:entity_type/apply",
+            post(handlers::description_templates::apply)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        // ========================================
+        // PRINT TEMPLATES (admin only)
+        // ========================================
+        .route(
+            "/api/sys/print-templates",
+            get(handlers::print_templates::list_all)
+                .post(handlers::print_templates::create)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/print-templates/:id",
+            axum::routing::put(handlers::print_templates::update)
+                .delete(handlers::print_templates::delete)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        // ========================================
+        // CONFIG BUNDLE EXPORT/IMPORT (admin only)
+        // ========================================
+        .route(
+            "/api/sys/config-bundle",
+            get(handlers::config_bundle::export)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        .route(
+            "/api/sys/config-bundle/import",
+            post(handlers::config_bundle::import)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        // ========================================
+        // SERVER LOG VIEWER (admin only)
+        // ========================================
+        .route(
+            "/api/sys/server-log",
+            get(handlers::log_viewer::query)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        // ========================================
+        // OIDC LOGIN SETTINGS (admin only)
+        // ========================================
+        .route(
+            "/api/sys/auth/oidc",
+            get(handlers::oidc::get_settings)
+                .put(handlers::oidc::save_settings)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
+        // ========================================
         // SYSTEM S3 FILE MANAGER
         // ========================================
         .route(
@@ -280,15 +727,26 @@ pub fn configure_system_routes() -> Router {
             axum::routing::delete(handlers::s3::delete_file)
                 .layer(middleware::from_fn(auth::middleware::require_admin)),
         )
+        .route(
+            "/api/sys/s3/search",
+            get(handlers::s3::search_text)
+                .layer(middleware::from_fn(auth::middleware::require_admin)),
+        )
         // ========================================
         // UTILITIES
         // ========================================
         // Logs handlers
         .route(
             "/api/logs",
-            get(handlers::logs::list_all)
-                .post(handlers::logs::create)
-                .delete(handlers::logs::clear_all),
+            get(handlers::logs::list_all).post(handlers::logs::create),
+        )
+        .route(
+            "/api/logs",
+            axum::routing::delete(handlers::logs::clear_all)
+                .layer(middleware::from_fn(
+                    crate::system::environment::require_confirmation,
+                ))
+                .layer(middleware::from_fn(auth::middleware::require_auth)),
         )
         // Form Settings handlers
         .route(
@@ -9,9 +9,11 @@ use contracts::system::{
 };
 
 use crate::system::access::{
-    primary_roles::{ADMIN_GRANTS, MANAGER_GRANTS, OPERATOR_GRANTS, VIEWER_GRANTS},
+    primary_roles::{
+        ADMIN_GRANTS, MANAGER_GRANTS, OPERATOR_GRANTS, VIEWER_GRANTS, WAREHOUSE_GRANTS,
+    },
     route_registry::ROUTE_REGISTRY,
-    scope_catalog::{find_scope, SCOPE_CATALOG},
+    scope_catalog::{find_scope, FIELD_SCOPES, SCOPE_CATALOG},
 };
 
 /// Serialize the full route registry as DTOs.
@@ -75,9 +77,10 @@ pub fn compute_violations() -> AuditReport {
         }
     }
 
-    // 3. Scope catalog entries not covered by any route (orphan)
+    // 3. Scope catalog entries not covered by any route (orphan);
+    //    field-level scopes are checked in handlers, not by routes
     for scope in SCOPE_CATALOG {
-        if !registry_scope_ids.contains(scope.scope_id) {
+        if !registry_scope_ids.contains(scope.scope_id) && !FIELD_SCOPES.contains(&scope.scope_id) {
             violations.push(ViolationEntry {
                 violation_type: ViolationType::OrphanScope,
                 subject: scope.scope_id.to_string(),
@@ -97,6 +100,7 @@ pub fn compute_violations() -> AuditReport {
         ("manager", MANAGER_GRANTS),
         ("operator", OPERATOR_GRANTS),
         ("viewer", VIEWER_GRANTS),
+        ("warehouse", WAREHOUSE_GRANTS),
     ];
 
     let role_coverage: Vec<CoverageStats> = role_grants
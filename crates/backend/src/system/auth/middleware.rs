@@ -11,6 +11,7 @@ pub async fn require_auth(mut req: Request<Body>, next: Next) -> Result<Response
     let claims = super::jwt::validate_token(&token)
         .await
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    attach_organization(&claims.sub, claims.is_admin, &mut req).await?;
     req.extensions_mut().insert(claims);
     Ok(next.run(req).await)
 }
@@ -24,6 +25,7 @@ pub async fn require_admin(mut req: Request<Body>, next: Next) -> Result<Respons
     if !claims.is_admin {
         return Err(StatusCode::FORBIDDEN);
     }
+    attach_organization(&claims.sub, claims.is_admin, &mut req).await?;
     req.extensions_mut().insert(claims);
     Ok(next.run(req).await)
 }
@@ -95,7 +97,12 @@ async fn check_scope_with_mode(
         }
     }
 
-    req.extensions_mut().insert(claims);
+    attach_organization(&claims.sub, claims.is_admin, &mut req).await?;
+    req.extensions_mut().insert(claims.clone());
+    if required_mode == AccessMode::All {
+        // Изменения документов агрегатов — в журнал «История»
+        return Ok(crate::system::document_audit::track(scope_id, &claims, req, next).await);
+    }
     Ok(next.run(req).await)
 }
 
@@ -119,6 +126,24 @@ pub async fn check_api_key(req: Request<Body>, next: Next) -> Result<Response, S
     Ok(next.run(req).await)
 }
 
+/// Active organization of the user (or of the request host) for list/detail filters.
+/// Non-admin users without any organization available are rejected with 403.
+async fn attach_organization(
+    user_id: &str,
+    is_admin: bool,
+    req: &mut Request<Body>,
+) -> Result<(), StatusCode> {
+    crate::system::org_context::service::attach(user_id, is_admin, req.extensions_mut())
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("Forbidden") {
+                return StatusCode::FORBIDDEN;
+            }
+            tracing::error!("Failed to resolve active organization: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
 /// Extract Bearer token string from Authorization header (returns owned String, no borrow issues).
 fn extract_bearer_token(req: &Request<Body>) -> Result<String, StatusCode> {
     let auth_header = req
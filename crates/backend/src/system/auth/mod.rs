@@ -1,4 +1,5 @@
 pub mod extractor;
 pub mod jwt;
 pub mod middleware;
+pub mod oidc;
 pub mod password;
@@ -0,0 +1,586 @@
+//! Вход через корпоративный OIDC-провайдер: authorization code flow с PKCE.
+//!
+//! `/oidc/start` перенаправляет браузер к провайдеру, `/oidc/callback` обменивает code
+//! на ID-токен, проверяет подпись по JWKS, issuer, audience и nonce, находит или создаёт
+//! пользователя и возвращает браузер на фронтенд с одноразовым кодом. Фронтенд меняет
+//! код на обычные access/refresh-токены (`/oidc/exchange`), так что токены не попадают
+//! в URL. Незавершённые входы и выданные коды живут в памяти процесса.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::Utc;
+use contracts::system::auth::{oidc_role_for_groups, OidcSettings};
+use contracts::system::users::User;
+use jsonwebtoken::{decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use once_cell::sync::Lazy;
+use rand::Rng;
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::shared::data::db::get_connection;
+use crate::system::auth::password;
+use crate::system::roles::repository as role_repository;
+use crate::system::settings::service as settings_service;
+use crate::system::users::repository as user_repository;
+use crate::system::users::service as user_service;
+
+/// Сколько ждём возврата пользователя от провайдера
+const PENDING_LOGIN_TTL: Duration = Duration::from_secs(10 * 60);
+/// Сколько живёт одноразовый код для фронтенда
+const LOGIN_CODE_TTL: Duration = Duration::from_secs(60);
+
+struct PendingLogin {
+    nonce: String,
+    code_verifier: String,
+    created: Instant,
+}
+
+struct IssuedCode {
+    user_id: String,
+    created: Instant,
+}
+
+static PENDING: Lazy<Mutex<HashMap<String, PendingLogin>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static ISSUED: Lazy<Mutex<HashMap<String, IssuedCode>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Deserialize)]
+struct Discovery {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+/// Данные пользователя из ID-токена.
+#[derive(Debug, Default)]
+struct OidcIdentity {
+    subject: String,
+    username: Option<String>,
+    email: Option<String>,
+    email_verified: bool,
+    full_name: Option<String>,
+    groups: Vec<String>,
+}
+
+fn random_token() -> String {
+    let mut rng = rand::rng();
+    let bytes: Vec<u8> = (0..32).map(|_| rng.random::<u8>()).collect();
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// Настройки, если вход через OIDC включён и заполнен.
+pub async fn enabled_settings() -> Result<OidcSettings> {
+    let settings = settings_service::get_oidc_settings().await?;
+    if !settings.enabled {
+        return Err(anyhow!("OIDC login is disabled"));
+    }
+    if settings.issuer_url.is_empty()
+        || settings.client_id.is_empty()
+        || settings.redirect_uri.is_empty()
+    {
+        return Err(anyhow!("OIDC login is not configured"));
+    }
+    Ok(settings)
+}
+
+/// Настройки для страницы администрирования: секрет не отдаётся.
+pub async fn settings_for_admin() -> Result<OidcSettings> {
+    let mut settings = settings_service::get_oidc_settings().await?;
+    settings.client_secret_set = !settings.client_secret.is_empty();
+    settings.client_secret.clear();
+    Ok(settings)
+}
+
+/// Проверяет и сохраняет настройки; пустой секрет оставляет сохранённый.
+pub async fn save_settings(mut settings: OidcSettings) -> Result<OidcSettings> {
+    let current = settings_service::get_oidc_settings().await?;
+    if settings.client_secret.is_empty() {
+        settings.client_secret = current.client_secret;
+    }
+    settings.issuer_url = settings.issuer_url.trim().trim_end_matches('/').to_string();
+    settings.client_id = settings.client_id.trim().to_string();
+    settings.redirect_uri = settings.redirect_uri.trim().to_string();
+    settings.groups_claim = settings.groups_claim.trim().to_string();
+    settings.default_role_code = settings
+        .default_role_code
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty());
+    settings
+        .role_mappings
+        .retain(|m| !m.group.trim().is_empty() && !m.role_code.trim().is_empty());
+
+    if settings.enabled {
+        if !settings.issuer_url.starts_with("https://")
+            && !settings.issuer_url.starts_with("http://")
+        {
+            return Err(anyhow!("Invalid issuer URL"));
+        }
+        if settings.client_id.is_empty() || settings.redirect_uri.is_empty() {
+            return Err(anyhow!(
+                "Invalid settings: client id and redirect URI are required"
+            ));
+        }
+        if !settings.scopes.split_whitespace().any(|s| s == "openid") {
+            return Err(anyhow!("Invalid scopes: openid is required"));
+        }
+    }
+    let role_codes = settings
+        .role_mappings
+        .iter()
+        .map(|m| m.role_code.as_str())
+        .chain(settings.default_role_code.as_deref());
+    for code in role_codes {
+        if role_repository::get_by_code(code).await?.is_none() {
+            return Err(anyhow!("Invalid role code: {}", code));
+        }
+    }
+
+    settings_service::set_oidc_settings(&settings).await?;
+    settings.client_secret_set = !settings.client_secret.is_empty();
+    settings.client_secret.clear();
+    Ok(settings)
+}
+
+async fn discover(issuer_url: &str) -> Result<Discovery> {
+    let url = format!(
+        "{}/.well-known/openid-configuration",
+        issuer_url.trim_end_matches('/')
+    );
+    reqwest::get(&url)
+        .await
+        .context("Failed to fetch OIDC discovery document")?
+        .error_for_status()?
+        .json::<Discovery>()
+        .await
+        .context("Invalid OIDC discovery document")
+}
+
+/// Адрес фронтенда, куда вернуть браузер после входа.
+pub fn post_login_url(settings: &OidcSettings) -> String {
+    if !settings.post_login_url.trim().is_empty() {
+        return settings.post_login_url.trim().to_string();
+    }
+    // Корень сервера из redirect_uri: https://host/api/... → https://host/
+    let uri = settings.redirect_uri.as_str();
+    let origin_end = uri
+        .find("://")
+        .and_then(|i| uri[i + 3..].find('/').map(|j| i + 3 + j))
+        .unwrap_or(uri.len());
+    format!("{}/", &uri[..origin_end])
+}
+
+/// Адрес авторизации у провайдера для нового входа.
+pub async fn authorization_url() -> Result<String> {
+    let settings = enabled_settings().await?;
+    let discovery = discover(&settings.issuer_url).await?;
+
+    let state = random_token();
+    let nonce = random_token();
+    let code_verifier = random_token();
+    let challenge = pkce_challenge(&code_verifier);
+
+    {
+        let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+        pending.retain(|_, p| p.created.elapsed() < PENDING_LOGIN_TTL);
+        pending.insert(
+            state.clone(),
+            PendingLogin {
+                nonce: nonce.clone(),
+                code_verifier,
+                created: Instant::now(),
+            },
+        );
+    }
+
+    let separator = if discovery.authorization_endpoint.contains('?') {
+        '&'
+    } else {
+        '?'
+    };
+    Ok(format!(
+        "{}{}response_type=code&client_id={}&redirect_uri={}&scope={}&state={}&nonce={}&code_challenge={}&code_challenge_method=S256",
+        discovery.authorization_endpoint,
+        separator,
+        urlencoding::encode(&settings.client_id),
+        urlencoding::encode(&settings.redirect_uri),
+        urlencoding::encode(&settings.scopes),
+        state,
+        nonce,
+        challenge
+    ))
+}
+
+fn string_claim(claims: &serde_json::Value, key: &str) -> Option<String> {
+    claims
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Группы из claim: массив строк или одна строка.
+fn groups_claim(claims: &serde_json::Value, key: &str) -> Vec<String> {
+    match claims.get(key) {
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(str::to_string)
+            .collect(),
+        Some(serde_json::Value::String(group)) => vec![group.clone()],
+        _ => Vec::new(),
+    }
+}
+
+fn identity_from_claims(claims: &serde_json::Value, groups_key: &str) -> Result<OidcIdentity> {
+    Ok(OidcIdentity {
+        subject: string_claim(claims, "sub").ok_or_else(|| anyhow!("ID token has no sub"))?,
+        username: string_claim(claims, "preferred_username"),
+        email: string_claim(claims, "email"),
+        // Адрес без явного email_verified=true не подтверждён: по нему не связываем
+        email_verified: claims
+            .get("email_verified")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        full_name: string_claim(claims, "name"),
+        groups: groups_claim(claims, groups_key),
+    })
+}
+
+async fn verify_id_token(
+    id_token: &str,
+    discovery: &Discovery,
+    settings: &OidcSettings,
+    nonce: &str,
+) -> Result<serde_json::Value> {
+    let header = decode_header(id_token).context("Invalid ID token header")?;
+    if matches!(
+        header.alg,
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+    ) {
+        return Err(anyhow!("Unsupported ID token algorithm {:?}", header.alg));
+    }
+
+    let jwks = reqwest::get(&discovery.jwks_uri)
+        .await
+        .context("Failed to fetch OIDC JWKS")?
+        .error_for_status()?
+        .json::<JwkSet>()
+        .await
+        .context("Invalid OIDC JWKS")?;
+    let jwk = match header.kid.as_deref() {
+        Some(kid) => jwks.find(kid),
+        None => jwks.keys.first(),
+    }
+    .ok_or_else(|| anyhow!("No JWKS key for ID token"))?;
+    let key = DecodingKey::from_jwk(jwk).context("Unsupported JWKS key")?;
+
+    let mut validation = Validation::new(header.alg);
+    validation.set_issuer(&[discovery.issuer.as_str()]);
+    validation.set_audience(&[settings.client_id.as_str()]);
+    let claims = decode::<serde_json::Value>(id_token, &key, &validation)
+        .context("ID token validation failed")?
+        .claims;
+
+    if string_claim(&claims, "nonce").as_deref() != Some(nonce) {
+        return Err(anyhow!("ID token nonce mismatch"));
+    }
+    Ok(claims)
+}
+
+async fn exchange_code(
+    code: &str,
+    pending: &PendingLogin,
+    discovery: &Discovery,
+    settings: &OidcSettings,
+) -> Result<String> {
+    let response = reqwest::Client::new()
+        .post(&discovery.token_endpoint)
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", settings.redirect_uri.as_str()),
+            ("client_id", settings.client_id.as_str()),
+            ("client_secret", settings.client_secret.as_str()),
+            ("code_verifier", pending.code_verifier.as_str()),
+        ])
+        .send()
+        .await
+        .context("Failed to call OIDC token endpoint")?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("OIDC token endpoint returned {}: {}", status, body));
+    }
+    Ok(response.json::<TokenResponse>().await?.id_token)
+}
+
+async fn find_identity(issuer: &str, subject: &str) -> Result<Option<String>> {
+    let row = get_connection()
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT user_id FROM sys_user_identities WHERE issuer = ? AND subject = ?",
+            [issuer.into(), subject.into()],
+        ))
+        .await?;
+    Ok(row.and_then(|r| r.try_get::<String>("", "user_id").ok()))
+}
+
+async fn save_identity(issuer: &str, subject: &str, user_id: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    get_connection()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO sys_user_identities (issuer, subject, user_id, created_at, last_login_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(issuer, subject) DO UPDATE SET user_id = excluded.user_id, last_login_at = excluded.last_login_at",
+            [
+                issuer.into(),
+                subject.into(),
+                user_id.into(),
+                now.clone().into(),
+                now.into(),
+            ],
+        ))
+        .await?;
+    Ok(())
+}
+
+/// Отметка `created_by` пользователей, созданных входом через провайдера.
+fn provisioned_by(issuer: &str) -> String {
+    format!("oidc:{}", issuer)
+}
+
+/// Роль и признак администратора существующего пользователя после входа. Группы
+/// провайдера управляют только пользователями, которых этот провайдер создал;
+/// права локальных учётных записей (в том числе администраторов) claims не меняют.
+fn synced_access(user: &User, issuer: &str, role_code: String, is_admin: bool) -> (String, bool) {
+    if user.created_by.as_deref() == Some(provisioned_by(issuer).as_str()) {
+        (role_code, is_admin)
+    } else {
+        (user.primary_role_code.clone(), user.is_admin)
+    }
+}
+
+/// Пользователь для внешней учётной записи: связанный ранее по `(issuer, sub)`,
+/// существующий с тем же подтверждённым провайдером email или новый. По логину
+/// (`preferred_username`) не связываем: его задаёт пользователь на стороне провайдера.
+async fn provision_user(
+    issuer: &str,
+    identity: &OidcIdentity,
+    settings: &OidcSettings,
+) -> Result<User> {
+    let (role_code, is_admin) = oidc_role_for_groups(settings, &identity.groups)
+        .ok_or_else(|| anyhow!("Нет доступа: группы пользователя не сопоставлены с ролями"))?;
+
+    let linked = match find_identity(issuer, &identity.subject).await? {
+        Some(user_id) => user_repository::get_by_id(&user_id).await?,
+        None => None,
+    };
+    let existing = match linked {
+        Some(user) => Some(user),
+        None => match identity.email.as_deref() {
+            Some(email) if identity.email_verified => {
+                user_repository::find_active_by_email(email).await?
+            }
+            _ => None,
+        },
+    };
+
+    let now = Utc::now().to_rfc3339();
+    let user = match existing {
+        Some(mut user) => {
+            if !user_service::is_access_allowed(&user, Utc::now().date_naive()) {
+                return Err(anyhow!("Учётная запись отключена"));
+            }
+            let (role_code, is_admin) = synced_access(&user, issuer, role_code, is_admin);
+            user.primary_role_code = role_code;
+            user.is_admin = is_admin;
+            if user.full_name.is_none() {
+                user.full_name = identity.full_name.clone();
+            }
+            user.updated_at = now;
+            user_repository::update(&user).await?;
+            user
+        }
+        None => {
+            if !settings.auto_create_users {
+                return Err(anyhow!(
+                    "Пользователь не найден. Обратитесь к администратору"
+                ));
+            }
+            let username = identity
+                .username
+                .clone()
+                .or_else(|| identity.email.clone())
+                .unwrap_or_else(|| identity.subject.clone());
+            let user = User {
+                id: uuid::Uuid::new_v4().to_string(),
+                username,
+                email: identity.email.clone().filter(|_| identity.email_verified),
+                full_name: identity.full_name.clone(),
+                is_active: true,
+                is_admin,
+                primary_role_code: role_code,
+                created_at: now.clone(),
+                updated_at: now,
+                last_login_at: None,
+                created_by: Some(provisioned_by(issuer)),
+                active_from: None,
+                active_until: None,
+            };
+            // Пароль не выдаётся: пользователь входит только через провайдера
+            let password_hash = password::hash_password(&random_token())?;
+            user_repository::create_with_password(&user, &password_hash).await?;
+            user
+        }
+    };
+
+    save_identity(issuer, &identity.subject, &user.id).await?;
+    let _ = user_repository::update_last_login(&user.id).await;
+    Ok(user)
+}
+
+/// Завершает вход по ответу провайдера; возвращает одноразовый код для фронтенда.
+pub async fn complete_login(code: &str, state: &str) -> Result<String> {
+    let pending = PENDING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(state)
+        .filter(|p| p.created.elapsed() < PENDING_LOGIN_TTL)
+        .ok_or_else(|| anyhow!("Сеанс входа устарел, повторите вход"))?;
+
+    let settings = enabled_settings().await?;
+    let discovery = discover(&settings.issuer_url).await?;
+    let id_token = exchange_code(code, &pending, &discovery, &settings).await?;
+    let claims = verify_id_token(&id_token, &discovery, &settings, &pending.nonce).await?;
+    let identity = identity_from_claims(&claims, &settings.groups_claim)?;
+    let user = provision_user(&discovery.issuer, &identity, &settings).await?;
+
+    tracing::info!(
+        "OIDC login: user {} ({}) via {}",
+        user.username,
+        user.id,
+        discovery.issuer
+    );
+
+    let login_code = random_token();
+    let mut issued = ISSUED.lock().unwrap_or_else(|e| e.into_inner());
+    issued.retain(|_, c| c.created.elapsed() < LOGIN_CODE_TTL);
+    issued.insert(
+        login_code.clone(),
+        IssuedCode {
+            user_id: user.id,
+            created: Instant::now(),
+        },
+    );
+    Ok(login_code)
+}
+
+/// Погашает одноразовый код; возвращает id пользователя.
+pub fn redeem_login_code(code: &str) -> Option<String> {
+    ISSUED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(code)
+        .filter(|c| c.created.elapsed() < LOGIN_CODE_TTL)
+        .map(|c| c.user_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pkce_challenge_matches_rfc7636_example() {
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-1mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn reads_identity_and_groups_from_claims() {
+        let claims = serde_json::json!({
+            "sub": "42",
+            "preferred_username": "ivanov",
+            "email": "ivanov@corp.example",
+            "roles": "sales",
+        });
+        let identity = identity_from_claims(&claims, "roles").unwrap();
+        assert_eq!(identity.subject, "42");
+        assert_eq!(identity.username.as_deref(), Some("ivanov"));
+        assert!(!identity.email_verified);
+        assert_eq!(identity.groups, vec!["sales".to_string()]);
+        assert!(identity_from_claims(&serde_json::json!({}), "groups").is_err());
+
+        let verified = serde_json::json!({ "sub": "42", "email_verified": true });
+        assert!(
+            identity_from_claims(&verified, "roles")
+                .unwrap()
+                .email_verified
+        );
+    }
+
+    #[test]
+    fn claims_do_not_change_local_account_access() {
+        let issuer = "https://idp.example.com";
+        let mut user = User {
+            id: "u1".to_string(),
+            username: "admin".to_string(),
+            email: None,
+            full_name: None,
+            is_active: true,
+            is_admin: true,
+            primary_role_code: "admin".to_string(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            last_login_at: None,
+            created_by: None,
+            active_from: None,
+            active_until: None,
+        };
+        assert_eq!(
+            synced_access(&user, issuer, "sales".to_string(), false),
+            ("admin".to_string(), true)
+        );
+
+        user.is_admin = false;
+        user.primary_role_code = "warehouse".to_string();
+        assert_eq!(
+            synced_access(&user, issuer, "admin".to_string(), true),
+            ("warehouse".to_string(), false)
+        );
+
+        user.created_by = Some(provisioned_by(issuer));
+        assert_eq!(
+            synced_access(&user, issuer, "sales".to_string(), true),
+            ("sales".to_string(), true)
+        );
+    }
+
+    #[test]
+    fn post_login_url_defaults_to_server_root() {
+        let mut settings = OidcSettings {
+            redirect_uri: "https://app.example.com/api/system/auth/oidc/callback".to_string(),
+            ..OidcSettings::default()
+        };
+        assert_eq!(post_login_url(&settings), "https://app.example.com/");
+        settings.post_login_url = "https://ui.example.com/".to_string();
+        assert_eq!(post_login_url(&settings), "https://ui.example.com/");
+    }
+}
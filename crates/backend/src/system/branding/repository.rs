@@ -0,0 +1,91 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, Set, Statement};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sys_org_branding")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub organization_id: String,
+    pub product_name: String,
+    pub accent_color: Option<String>,
+    pub logo_url: Option<String>,
+    /// Хосты через запятую (уже нормализованные)
+    pub hosts: String,
+    pub is_default: bool,
+    pub updated_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+fn conn() -> &'static DatabaseConnection {
+    get_connection()
+}
+
+pub async fn list_all() -> Result<Vec<Model>, DbErr> {
+    Entity::find().all(conn()).await
+}
+
+pub async fn find(organization_id: &str) -> Result<Option<Model>, DbErr> {
+    Entity::find_by_id(organization_id.to_string())
+        .one(conn())
+        .await
+}
+
+/// Неудалённые организации a002: (id, наименование), по наименованию.
+pub async fn list_organizations() -> Result<Vec<(String, String)>, DbErr> {
+    let rows = conn()
+        .query_all(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT id, description FROM a002_organization WHERE is_deleted = 0 ORDER BY description"
+                .to_string(),
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|r| {
+            Some((
+                r.try_get::<String>("", "id").ok()?,
+                r.try_get::<String>("", "description").unwrap_or_default(),
+            ))
+        })
+        .collect())
+}
+
+/// Сохраняет настройки; `is_default` снимается с остальных организаций.
+pub async fn upsert(model: Model) -> Result<(), DbErr> {
+    if model.is_default {
+        Entity::update_many()
+            .col_expr(Column::IsDefault, Expr::value(false))
+            .filter(Column::OrganizationId.ne(model.organization_id.clone()))
+            .exec(conn())
+            .await?;
+    }
+    let exists = find(&model.organization_id).await?.is_some();
+    let active = ActiveModel {
+        organization_id: Set(model.organization_id),
+        product_name: Set(model.product_name),
+        accent_color: Set(model.accent_color),
+        logo_url: Set(model.logo_url),
+        hosts: Set(model.hosts),
+        is_default: Set(model.is_default),
+        updated_at: Set(model.updated_at),
+    };
+    if exists {
+        active.update(conn()).await?;
+    } else {
+        active.insert(conn()).await?;
+    }
+    Ok(())
+}
+
+pub async fn delete(organization_id: &str) -> Result<(), DbErr> {
+    Entity::delete_by_id(organization_id.to_string())
+        .exec(conn())
+        .await?;
+    Ok(())
+}
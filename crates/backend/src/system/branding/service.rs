@@ -0,0 +1,210 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use contracts::system::branding::{
+    is_valid_accent_color, normalize_host, BrandingDto, OrgBrandingDto, OrgBrandingSettings,
+    OrgBrandingUpsertRequest, MAX_LOGO_URL_LEN,
+};
+
+use super::repository;
+
+fn split_hosts(hosts: &str) -> Vec<String> {
+    hosts
+        .split(',')
+        .map(normalize_host)
+        .filter(|h| !h.is_empty())
+        .collect()
+}
+
+fn settings_from_model(model: &repository::Model) -> OrgBrandingSettings {
+    OrgBrandingSettings {
+        product_name: model.product_name.clone(),
+        accent_color: model.accent_color.clone(),
+        logo_url: model.logo_url.clone(),
+        hosts: split_hosts(&model.hosts),
+        is_default: model.is_default,
+    }
+}
+
+fn dto_from_model(model: repository::Model) -> BrandingDto {
+    BrandingDto {
+        organization_id: Some(model.organization_id),
+        product_name: model.product_name,
+        accent_color: model.accent_color,
+        logo_url: model.logo_url,
+    }
+}
+
+/// Строка брендирования для хоста: совпадение по `hosts`, иначе `is_default`.
+pub(crate) fn pick_for_host<'a>(
+    rows: &'a [repository::Model],
+    host: Option<&str>,
+) -> Option<&'a repository::Model> {
+    let host = host.map(normalize_host).filter(|h| !h.is_empty());
+    let by_host = host.and_then(|host| rows.iter().find(|r| split_hosts(&r.hosts).contains(&host)));
+    by_host.or_else(|| rows.iter().find(|r| r.is_default))
+}
+
+/// Брендирование для хоста запроса; без настроек — стандартный вид.
+pub async fn resolve(host: Option<&str>) -> Result<BrandingDto> {
+    let rows = repository::list_all().await?;
+    Ok(pick_for_host(&rows, host)
+        .cloned()
+        .map(dto_from_model)
+        .unwrap_or_default())
+}
+
+/// Все организации с их брендированием (для страницы администрирования).
+pub async fn list() -> Result<Vec<OrgBrandingDto>> {
+    let rows = repository::list_all().await?;
+    let organizations = repository::list_organizations().await?;
+    Ok(organizations
+        .into_iter()
+        .map(|(organization_id, organization_name)| {
+            let row = rows.iter().find(|r| r.organization_id == organization_id);
+            OrgBrandingDto {
+                settings: row.map(settings_from_model),
+                updated_at: row.map(|r| r.updated_at.clone()),
+                organization_id,
+                organization_name,
+            }
+        })
+        .collect())
+}
+
+fn normalize_optional(value: Option<String>) -> Option<String> {
+    value
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn validate(settings: &OrgBrandingSettings) -> Result<()> {
+    let name = settings.product_name.trim();
+    if name.is_empty() || name.chars().count() > 60 {
+        return Err(anyhow!(
+            "Invalid product name: required, up to 60 characters"
+        ));
+    }
+    if let Some(color) = settings.accent_color.as_deref() {
+        if !is_valid_accent_color(color) {
+            return Err(anyhow!("Invalid accent color, expected #rrggbb"));
+        }
+    }
+    if let Some(logo) = settings.logo_url.as_deref() {
+        let allowed = logo.starts_with("https://")
+            || logo.starts_with("http://")
+            || logo.starts_with('/')
+            || logo.starts_with("data:image/");
+        if !allowed || logo.len() > MAX_LOGO_URL_LEN {
+            return Err(anyhow!(
+                "Invalid logo: expected http(s) URL, site path or data:image URI up to 256 KB"
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub async fn upsert(req: OrgBrandingUpsertRequest) -> Result<OrgBrandingDto> {
+    let organizations = repository::list_organizations().await?;
+    let organization_name = organizations
+        .into_iter()
+        .find(|(id, _)| *id == req.organization_id)
+        .map(|(_, name)| name)
+        .ok_or_else(|| anyhow!("Organization not found"))?;
+
+    let mut settings = req.settings;
+    settings.product_name = settings.product_name.trim().to_string();
+    settings.accent_color = normalize_optional(settings.accent_color).map(|c| c.to_lowercase());
+    settings.logo_url = normalize_optional(settings.logo_url);
+    settings.hosts = split_hosts(&settings.hosts.join(","));
+    validate(&settings)?;
+
+    // Хост может принадлежать только одной организации
+    for row in repository::list_all().await? {
+        if row.organization_id == req.organization_id {
+            continue;
+        }
+        if let Some(host) = split_hosts(&row.hosts)
+            .into_iter()
+            .find(|h| settings.hosts.contains(h))
+        {
+            return Err(anyhow!(
+                "Invalid hosts: {} is already used by another organization",
+                host
+            ));
+        }
+    }
+
+    let updated_at = Utc::now().to_rfc3339();
+    repository::upsert(repository::Model {
+        organization_id: req.organization_id.clone(),
+        product_name: settings.product_name.clone(),
+        accent_color: settings.accent_color.clone(),
+        logo_url: settings.logo_url.clone(),
+        hosts: settings.hosts.join(","),
+        is_default: settings.is_default,
+        updated_at: updated_at.clone(),
+    })
+    .await?;
+
+    Ok(OrgBrandingDto {
+        organization_id: req.organization_id,
+        organization_name,
+        settings: Some(settings),
+        updated_at: Some(updated_at),
+    })
+}
+
+pub async fn delete(organization_id: &str) -> Result<()> {
+    repository::find(organization_id)
+        .await?
+        .ok_or_else(|| anyhow!("Branding not found"))?;
+    repository::delete(organization_id).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(id: &str, hosts: &str, is_default: bool) -> repository::Model {
+        repository::Model {
+            organization_id: id.to_string(),
+            product_name: id.to_string(),
+            accent_color: None,
+            logo_url: None,
+            hosts: hosts.to_string(),
+            is_default,
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn host_match_wins_over_default() {
+        let rows = vec![
+            row("a", "a.example.com", true),
+            row("b", "b.example.com,b.local", false),
+        ];
+        let picked = pick_for_host(&rows, Some("B.example.com:8080")).unwrap();
+        assert_eq!(picked.organization_id, "b");
+        let picked = pick_for_host(&rows, Some("other.example.com")).unwrap();
+        assert_eq!(picked.organization_id, "a");
+        assert!(pick_for_host(&[row("b", "b.local", false)], None).is_none());
+    }
+
+    #[test]
+    fn validates_settings() {
+        let mut settings = OrgBrandingSettings {
+            product_name: "Seller Hub".to_string(),
+            accent_color: Some("#1a2b3c".to_string()),
+            logo_url: Some("https://cdn.example.com/logo.svg".to_string()),
+            hosts: vec![],
+            is_default: false,
+        };
+        assert!(validate(&settings).is_ok());
+        settings.accent_color = Some("red".to_string());
+        assert!(validate(&settings).is_err());
+        settings.accent_color = None;
+        settings.logo_url = Some("javascript:alert(1)".to_string());
+        assert!(validate(&settings).is_err());
+    }
+}
@@ -0,0 +1,318 @@
+//! Пакетные действия по вставленному списку номеров (SRID WB, номера отправлений Ozon).
+//!
+//! Номер ищется по `document_no` во всех поддерживаемых агрегатах; найденные
+//! документы можно провести или пометить меткой (`sys_document_tags`) — пакетными
+//! операциями `post_by_list` / `tag_by_list` (см. [`crate::system::operations`]).
+
+use anyhow::{bail, Result};
+use chrono::Utc;
+use contracts::system::bulk_ops::{
+    BulkActionResultDto, BulkDocumentRef, BulkLookupItemDto, BulkLookupResponse,
+    BULK_LOOKUP_MAX_IDS,
+};
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement, Value};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+use crate::system::operations::service::Progress;
+
+/// Агрегаты, в которых ищется номер; у всех `document_no` — номер маркетплейса.
+const LOOKUP_ENTITY_TYPES: &[&str] = &[
+    "a012_wb_sales",
+    "a015_wb_orders",
+    "a010_ozon_fbs_posting",
+    "a011_ozon_fbo_posting",
+];
+
+/// Параметров в одном `IN (...)` — с запасом ниже лимита SQLite.
+const SQL_CHUNK: usize = 500;
+const MAX_TAG_LEN: usize = 64;
+/// Сколько текстов ошибок возвращать в ответе.
+const MAX_ERRORS: usize = 20;
+
+fn placeholders(n: usize) -> String {
+    vec!["?"; n].join(", ")
+}
+
+/// Ищет документы по номерам, включая архив. Один номер может найтись в нескольких
+/// агрегатах (продажа и заказ WB с одним SRID) — тогда в ответе будут обе строки.
+/// `organization_id` — активная организация пользователя: документы других
+/// организаций считаются ненайденными.
+pub async fn lookup(ids: &[String], organization_id: Option<&str>) -> Result<BulkLookupResponse> {
+    if ids.len() > BULK_LOOKUP_MAX_IDS {
+        bail!(
+            "Слишком длинный список: {} (максимум {})",
+            ids.len(),
+            BULK_LOOKUP_MAX_IDS
+        );
+    }
+    let mut items = find_documents_with_conn(get_connection(), ids, organization_id).await?;
+
+    let mut tags = load_tags(&items).await?;
+    for item in &mut items {
+        if let Some(t) = tags.remove(&(item.entity_type.clone(), item.id.clone())) {
+            item.tags = t;
+        }
+    }
+
+    // Порядок — как во вставленном списке
+    let order: HashMap<&str, usize> = ids
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.as_str(), i))
+        .collect();
+    items.sort_by_key(|i| order.get(i.input.as_str()).copied().unwrap_or(usize::MAX));
+
+    let found: HashSet<&str> = items.iter().map(|i| i.input.as_str()).collect();
+    let not_found = ids
+        .iter()
+        .filter(|s| !found.contains(s.as_str()))
+        .cloned()
+        .collect();
+
+    Ok(BulkLookupResponse { items, not_found })
+}
+
+/// Документы с номерами `ids` во всех агрегатах поиска, включая архив; при
+/// заданной `organization_id` — только документы этой организации.
+async fn find_documents_with_conn<C: ConnectionTrait>(
+    db: &C,
+    ids: &[String],
+    organization_id: Option<&str>,
+) -> Result<Vec<BulkLookupItemDto>> {
+    let mut items: Vec<BulkLookupItemDto> = Vec::new();
+
+    for entity_type in LOOKUP_ENTITY_TYPES {
+        for chunk in ids.chunks(SQL_CHUNK) {
+            let mut sql = format!(
+                "SELECT id, document_no, description, is_posted FROM {} \
+                 WHERE is_deleted = 0 AND document_no IN ({})",
+                projection_archive::source(entity_type, None),
+                placeholders(chunk.len())
+            );
+            let mut values: Vec<Value> = chunk.iter().map(|s| s.clone().into()).collect();
+            if let Some(organization_id) = organization_id {
+                sql.push_str(
+                    " AND LOWER(TRIM(REPLACE(COALESCE(json_extract(header_json, '$.organization_id'), ''), '\"', ''))) \
+                     = LOWER(TRIM(REPLACE(?, '\"', '')))",
+                );
+                values.push(organization_id.into());
+            }
+            let rows = db
+                .query_all(Statement::from_sql_and_values(
+                    DatabaseBackend::Sqlite,
+                    &sql,
+                    values,
+                ))
+                .await?;
+            for row in rows {
+                let document_no: String = row.try_get("", "document_no")?;
+                items.push(BulkLookupItemDto {
+                    input: document_no.clone(),
+                    entity_type: entity_type.to_string(),
+                    id: row.try_get("", "id")?,
+                    document_no,
+                    description: row.try_get("", "description")?,
+                    is_posted: row.try_get::<i32>("", "is_posted")? != 0,
+                    tags: Vec::new(),
+                });
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+async fn load_tags(items: &[BulkLookupItemDto]) -> Result<HashMap<(String, String), Vec<String>>> {
+    let conn = get_connection();
+    let mut result: HashMap<(String, String), Vec<String>> = HashMap::new();
+    let ids: Vec<&str> = items.iter().map(|i| i.id.as_str()).collect();
+    for chunk in ids.chunks(SQL_CHUNK) {
+        let sql = format!(
+            "SELECT entity_type, entity_id, tag FROM sys_document_tags \
+             WHERE entity_id IN ({}) ORDER BY tag",
+            placeholders(chunk.len())
+        );
+        let values: Vec<Value> = chunk.iter().map(|s| s.to_string().into()).collect();
+        let rows = conn
+            .query_all(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                &sql,
+                values,
+            ))
+            .await?;
+        for row in rows {
+            let key = (
+                row.try_get("", "entity_type")?,
+                row.try_get("", "entity_id")?,
+            );
+            result.entry(key).or_default().push(row.try_get("", "tag")?);
+        }
+    }
+    Ok(result)
+}
+
+async fn post_one(entity_type: &str, id: Uuid) -> Result<()> {
+    match entity_type {
+        "a012_wb_sales" => crate::domain::a012_wb_sales::posting::post_document(id).await,
+        "a015_wb_orders" => crate::domain::a015_wb_orders::posting::post_document(id).await,
+        "a010_ozon_fbs_posting" => {
+            crate::domain::a010_ozon_fbs_posting::posting::post_document(id).await
+        }
+        "a011_ozon_fbo_posting" => {
+            crate::domain::a011_ozon_fbo_posting::posting::post_document(id).await
+        }
+        other => bail!("Batch posting is not supported for '{other}'"),
+    }
+}
+
+fn push_error(result: &mut BulkActionResultDto, doc: &BulkDocumentRef, e: anyhow::Error) {
+    result.failed += 1;
+    if result.errors.len() < MAX_ERRORS {
+        result.errors.push(format!("{}: {}", doc.id, e));
+    }
+}
+
+/// Проводит документы по одному (одна транзакция на документ).
+pub async fn post_documents(
+    documents: &[BulkDocumentRef],
+    progress: &mut Progress,
+) -> Result<BulkActionResultDto> {
+    let mut result = BulkActionResultDto::default();
+    for doc in documents {
+        let outcome = match Uuid::parse_str(&doc.id) {
+            Ok(id) => post_one(&doc.entity_type, id).await,
+            Err(e) => Err(e.into()),
+        };
+        match outcome {
+            Ok(()) => result.succeeded += 1,
+            Err(e) => {
+                tracing::warn!("Batch post {} {}: {}", doc.entity_type, doc.id, e);
+                push_error(&mut result, doc, e);
+            }
+        }
+        progress.advance().await;
+    }
+    tracing::info!(
+        "Batch post by ID list: succeeded {}, failed {}",
+        result.succeeded,
+        result.failed
+    );
+    Ok(result)
+}
+
+/// Проверка метки до запуска операции: обрезанная метка или ошибка для пользователя.
+pub fn normalize_tag(tag: &str) -> Result<&str> {
+    let tag = tag.trim();
+    if tag.is_empty() || tag.chars().count() > MAX_TAG_LEN {
+        bail!("Метка должна быть от 1 до {} символов", MAX_TAG_LEN);
+    }
+    Ok(tag)
+}
+
+/// Ставит метку документам; повторная метка не дублируется.
+pub async fn tag_documents(
+    documents: &[BulkDocumentRef],
+    tag: &str,
+    user_id: &str,
+    progress: &mut Progress,
+) -> Result<BulkActionResultDto> {
+    let tag = normalize_tag(tag)?;
+    let conn = get_connection();
+    let now = Utc::now().to_rfc3339();
+    let mut result = BulkActionResultDto::default();
+    for doc in documents {
+        if !LOOKUP_ENTITY_TYPES.contains(&doc.entity_type.as_str()) {
+            push_error(
+                &mut result,
+                doc,
+                anyhow::anyhow!("Tagging is not supported for '{}'", doc.entity_type),
+            );
+            progress.advance().await;
+            continue;
+        }
+        let outcome = conn
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "INSERT OR IGNORE INTO sys_document_tags \
+                 (entity_type, entity_id, tag, created_at, created_by) VALUES (?, ?, ?, ?, ?)",
+                [
+                    doc.entity_type.clone().into(),
+                    doc.id.clone().into(),
+                    tag.into(),
+                    now.clone().into(),
+                    user_id.into(),
+                ],
+            ))
+            .await;
+        match outcome {
+            Ok(_) => result.succeeded += 1,
+            Err(e) => push_error(&mut result, doc, e.into()),
+        }
+        progress.advance().await;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{ConnectOptions, Database};
+
+    const ORG_A: &str = "5f0c8a8e-1111-4a4a-9b9b-000000000001";
+    const ORG_B: &str = "5f0c8a8e-2222-4a4a-9b9b-000000000002";
+
+    #[tokio::test]
+    async fn lookup_of_organization_skips_documents_of_another() {
+        projection_archive::set_cached_boundary(Some("2025-01".to_string()));
+        let mut options = ConnectOptions::new("sqlite::memory:");
+        options.max_connections(1);
+        let db = Database::connect(options).await.unwrap();
+        for table in LOOKUP_ENTITY_TYPES
+            .iter()
+            .copied()
+            .chain(["a012_wb_sales_archive"])
+        {
+            db.execute_unprepared(&format!(
+                "CREATE TABLE {table} (id TEXT PRIMARY KEY, document_no TEXT, description TEXT, \
+                 is_posted INTEGER, is_deleted INTEGER, header_json TEXT)"
+            ))
+            .await
+            .unwrap();
+        }
+        // У каждой организации — продажа (одна из них в архиве) и заказ с тем же SRID.
+        db.execute_unprepared(&format!(
+            "INSERT INTO a012_wb_sales VALUES
+                 ('sale-a', 'SRID-A', 'A', 1, 0, '{{\"organization_id\": \"\\\"{a}\\\"\"}}'),
+                 ('sale-b', 'SRID-B', 'B', 1, 0, '{{\"organization_id\": \"{b}\"}}');
+             INSERT INTO a012_wb_sales_archive VALUES
+                 ('old-a', 'OLD-A', 'A', 1, 0, '{{\"organization_id\": \"{a}\"}}'),
+                 ('old-b', 'OLD-B', 'B', 1, 0, '{{\"organization_id\": \"{b}\"}}');
+             INSERT INTO a015_wb_orders VALUES
+                 ('order-a', 'SRID-A', 'A', 0, 0, '{{\"organization_id\": \"{a}\"}}'),
+                 ('order-b', 'SRID-B', 'B', 0, 0, '{{\"organization_id\": \"{b}\"}}');",
+            a = ORG_A.to_uppercase(),
+            b = ORG_B,
+        ))
+        .await
+        .unwrap();
+        let ids: Vec<String> = ["SRID-A", "SRID-B", "OLD-A", "OLD-B"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+
+        let mut found: Vec<String> = find_documents_with_conn(&db, &ids, Some(ORG_A))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|item| item.id)
+            .collect();
+        found.sort();
+        assert_eq!(found, vec!["old-a", "order-a", "sale-a"]);
+
+        let all = find_documents_with_conn(&db, &ids, None).await.unwrap();
+        assert_eq!(all.len(), 6);
+    }
+}
@@ -0,0 +1,19 @@
+//! Журнал массовых разрушающих операций и их отмена.
+//!
+//! Пакетная отмена проведения (a012 / a013 / a016) записывает снимок прежнего
+//! состояния — id документов, которые были проведены до операции. В течение окна
+//! (`sys_settings.bulk_undo_window_minutes`) операцию можно отменить одной кнопкой
+//! со страницы «История операций»: документы из снимка перепроводятся одной
+//! транзакцией — восстанавливается либо весь снимок, либо ничего. Других видов
+//! массовых операций со снимком нет, их отмена отклоняется.
+//!
+//! Там же — действия по вставленному списку номеров ([`lookup`]): поиск документов
+//! по SRID / номеру отправления, пакетное проведение, метки и выгрузка.
+//!
+//! [`reassign`] — исправление организации / маркетплейса в исторических документах
+//! вместе с проекцией p900, с пробным прогоном (dry-run).
+
+pub mod lookup;
+pub mod reassign;
+pub mod repository;
+pub mod service;
@@ -0,0 +1,479 @@
+//! Переназначение организации / маркетплейса в исторических документах.
+//!
+//! Ранние импорты записывали в заголовок неверные `organization_id` / `marketplace_id`.
+//! Переназначение меняет их в `header_json` (и в денормализованной колонке, где она есть)
+//! у документов под отбором и `organization_ref` в строках `p900_sales_register`
+//! (включая архив) с `registrator_ref` этих документов — всё в одной транзакции,
+//! вместе с событиями ленты CDC по изменённым документам и их строкам p900.
+//! Архивированные документы (`a012_wb_sales_archive`) отбираются и меняются
+//! на месте вместе с рабочими.
+//! Маркетплейс в p900 задаётся типом документа и не меняется.
+//!
+//! `dry_run` выполняет те же изменения и откатывает транзакцию: отчёт показывает
+//! точное число затронутых строк.
+
+use std::collections::BTreeSet;
+
+use anyhow::{bail, Result};
+use chrono::{NaiveDate, Utc};
+use contracts::system::bulk_ops::{
+    BulkReassignFilter, BulkReassignReportDto, BulkReassignRequest, BulkReassignSampleDto,
+    BulkReassignTableDto, BULK_LOOKUP_MAX_IDS,
+};
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, DatabaseTransaction, Statement, TransactionTrait, Value,
+};
+
+use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+use crate::system::cdc::service::{self as cdc, ChangeEvent, ChangeOp};
+
+/// Таблицы p900, где `organization_ref` копируется из заголовка документа.
+const PROJECTION_TABLES: &[&str] = &["p900_sales_register", "p900_sales_register_archive"];
+
+/// Тип проекции в ленте CDC (как при проведении a012; архив — та же проекция).
+const PROJECTION_CHANGE_TYPE: &str = "p900_mp_sales_register";
+
+/// Сколько документов показывать в отчёте.
+const SAMPLE_SIZE: usize = 20;
+
+struct EntitySpec {
+    entity_type: &'static str,
+    /// Денормализованная колонка `organization_id` рядом с `header_json`
+    organization_column: bool,
+    /// Ожидаемый `a005_marketplace.marketplace_type` для нового маркетплейса
+    marketplace_type: &'static str,
+}
+
+const SPECS: &[EntitySpec] = &[
+    EntitySpec {
+        entity_type: "a012_wb_sales",
+        organization_column: true,
+        marketplace_type: "mp-wb",
+    },
+    EntitySpec {
+        entity_type: "a013_ym_order",
+        organization_column: true,
+        marketplace_type: "mp-ym",
+    },
+    EntitySpec {
+        entity_type: "a015_wb_orders",
+        organization_column: false,
+        marketplace_type: "mp-wb",
+    },
+    EntitySpec {
+        entity_type: "a016_ym_returns",
+        organization_column: false,
+        marketplace_type: "mp-ym",
+    },
+];
+
+fn spec(entity_type: &str) -> Result<&'static EntitySpec> {
+    match SPECS.iter().find(|s| s.entity_type == entity_type) {
+        Some(spec) => Ok(spec),
+        None => bail!("Invalid entity type for reassignment: '{entity_type}'"),
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+fn parse_date(value: &str, field: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("Invalid {field}: '{value}' (ожидается YYYY-MM-DD)"))
+}
+
+/// Условие `WHERE` для отбора документов и его параметры. Отбор без сужающих
+/// критериев отклоняется: переназначение всей таблицы — почти наверняка ошибка.
+fn build_filter(filter: &BulkReassignFilter) -> Result<(String, Vec<Value>)> {
+    let mut conditions = vec!["is_deleted = 0".to_string()];
+    let mut values: Vec<Value> = Vec::new();
+    let mut narrowed = false;
+
+    let ids: Vec<&str> = filter
+        .ids
+        .iter()
+        .map(|id| id.trim())
+        .filter(|id| !id.is_empty())
+        .collect();
+    if ids.len() > BULK_LOOKUP_MAX_IDS {
+        bail!(
+            "Invalid filter: слишком много id ({}, максимум {})",
+            ids.len(),
+            BULK_LOOKUP_MAX_IDS
+        );
+    }
+    if !ids.is_empty() {
+        conditions.push(format!("id IN ({})", vec!["?"; ids.len()].join(", ")));
+        values.extend(ids.into_iter().map(|id| Value::from(id.to_string())));
+        narrowed = true;
+    }
+    for (field, value) in [
+        ("organization_id", &filter.organization_id),
+        ("marketplace_id", &filter.marketplace_id),
+        ("connection_id", &filter.connection_id),
+    ] {
+        if let Some(value) = non_empty(value) {
+            conditions.push(format!("json_extract(header_json, '$.{field}') = ?"));
+            values.push(value.into());
+            narrowed = true;
+        }
+    }
+
+    let from = non_empty(&filter.created_from)
+        .map(|d| parse_date(&d, "created_from"))
+        .transpose()?;
+    let to = non_empty(&filter.created_to)
+        .map(|d| parse_date(&d, "created_to"))
+        .transpose()?;
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            bail!("Invalid filter: created_from позже created_to");
+        }
+    }
+    if let Some(from) = from {
+        conditions.push("substr(created_at, 1, 10) >= ?".to_string());
+        values.push(from.format("%Y-%m-%d").to_string().into());
+        narrowed = true;
+    }
+    if let Some(to) = to {
+        conditions.push("substr(created_at, 1, 10) <= ?".to_string());
+        values.push(to.format("%Y-%m-%d").to_string().into());
+        narrowed = true;
+    }
+
+    if !narrowed {
+        bail!("Invalid filter: укажите id, организацию, маркетплейс, подключение или период");
+    }
+    Ok((conditions.join(" AND "), values))
+}
+
+/// Условие «значение изменится» для выбранных полей заголовка и его параметры.
+fn changed_condition(
+    organization_id: Option<&str>,
+    marketplace_id: Option<&str>,
+) -> (String, Vec<Value>) {
+    let mut parts = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+    for (field, value) in [
+        ("organization_id", organization_id),
+        ("marketplace_id", marketplace_id),
+    ] {
+        if let Some(value) = value {
+            parts.push(format!("json_extract(header_json, '$.{field}') IS NOT ?"));
+            values.push(value.to_string().into());
+        }
+    }
+    (format!("({})", parts.join(" OR ")), values)
+}
+
+async fn count(txn: &DatabaseTransaction, sql: &str, values: Vec<Value>) -> Result<i64> {
+    let row = txn
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            sql,
+            values,
+        ))
+        .await?;
+    Ok(match row {
+        Some(row) => row.try_get("", "cnt")?,
+        None => 0,
+    })
+}
+
+/// Проверяет, что новая организация / маркетплейс существуют и маркетплейс
+/// соответствует типу документа.
+async fn validate_targets(
+    txn: &DatabaseTransaction,
+    spec: &EntitySpec,
+    organization_id: Option<&str>,
+    marketplace_id: Option<&str>,
+) -> Result<()> {
+    if let Some(id) = organization_id {
+        let sql = "SELECT COUNT(*) AS cnt FROM a002_organization WHERE id = ? AND is_deleted = 0";
+        if count(txn, sql, vec![id.to_string().into()]).await? == 0 {
+            bail!("Invalid organization: '{id}' не найдена");
+        }
+    }
+    if let Some(id) = marketplace_id {
+        let row = txn
+            .query_one(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "SELECT marketplace_type FROM a005_marketplace WHERE id = ? AND is_deleted = 0",
+                vec![id.to_string().into()],
+            ))
+            .await?;
+        let Some(row) = row else {
+            bail!("Invalid marketplace: '{id}' не найден");
+        };
+        let marketplace_type: Option<String> = row.try_get("", "marketplace_type")?;
+        if marketplace_type.as_deref() != Some(spec.marketplace_type) {
+            bail!(
+                "Invalid marketplace: тип {} не подходит для {} (нужен {})",
+                marketplace_type.as_deref().unwrap_or("—"),
+                spec.entity_type,
+                spec.marketplace_type
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn load_sample(
+    txn: &DatabaseTransaction,
+    table: &str,
+    where_sql: &str,
+    values: Vec<Value>,
+) -> Result<Vec<BulkReassignSampleDto>> {
+    let sql = format!(
+        "SELECT id, \
+                COALESCE(json_extract(header_json, '$.document_no'), '') AS document_no, \
+                COALESCE(json_extract(header_json, '$.organization_id'), '') AS organization_id, \
+                COALESCE(json_extract(header_json, '$.marketplace_id'), '') AS marketplace_id \
+         FROM {table} WHERE {where_sql} ORDER BY created_at LIMIT {SAMPLE_SIZE}"
+    );
+    let rows = txn
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            &sql,
+            values,
+        ))
+        .await?;
+    rows.into_iter()
+        .map(|row| {
+            Ok(BulkReassignSampleDto {
+                id: row.try_get("", "id")?,
+                document_no: row.try_get("", "document_no")?,
+                organization_id: row.try_get("", "organization_id")?,
+                marketplace_id: row.try_get("", "marketplace_id")?,
+            })
+        })
+        .collect()
+}
+
+/// Переназначает организацию и/или маркетплейс документам под отбором.
+pub async fn reassign(req: &BulkReassignRequest, user_id: &str) -> Result<BulkReassignReportDto> {
+    let spec = spec(&req.entity_type)?;
+    let organization_id = non_empty(&req.set_organization_id);
+    let marketplace_id = non_empty(&req.set_marketplace_id);
+    if organization_id.is_none() && marketplace_id.is_none() {
+        bail!("Invalid request: укажите новую организацию и/или маркетплейс");
+    }
+    let (where_sql, where_values) = build_filter(&req.filter)?;
+    let table = spec.entity_type;
+    let source = projection_archive::source(table, None);
+
+    let txn = get_connection().begin().await?;
+    validate_targets(
+        &txn,
+        spec,
+        organization_id.as_deref(),
+        marketplace_id.as_deref(),
+    )
+    .await?;
+
+    let matched = count(
+        &txn,
+        &format!("SELECT COUNT(*) AS cnt FROM {source} WHERE {where_sql}"),
+        where_values.clone(),
+    )
+    .await? as usize;
+    let sample = load_sample(&txn, &source, &where_sql, where_values.clone()).await?;
+
+    // Проекции — до документов: после обновления заголовков отбор по старой
+    // организации уже не найдёт их
+    let mut projections = Vec::new();
+    let mut events = Vec::new();
+    if let Some(org) = &organization_id {
+        let mut registrators = BTreeSet::new();
+        for projection in PROJECTION_TABLES {
+            let condition = format!(
+                "organization_ref <> ? \
+                 AND registrator_ref IN (SELECT id FROM {source} WHERE {where_sql})"
+            );
+            let mut condition_values: Vec<Value> = vec![org.clone().into()];
+            condition_values.extend(where_values.iter().cloned());
+            let rows = txn
+                .query_all(Statement::from_sql_and_values(
+                    DatabaseBackend::Sqlite,
+                    &format!("SELECT DISTINCT registrator_ref FROM {projection} WHERE {condition}"),
+                    condition_values.clone(),
+                ))
+                .await?;
+            for row in rows {
+                registrators.insert(row.try_get::<String>("", "registrator_ref")?);
+            }
+
+            let sql = format!("UPDATE {projection} SET organization_ref = ? WHERE {condition}");
+            let mut values: Vec<Value> = vec![org.clone().into()];
+            values.extend(condition_values);
+            let result = txn
+                .execute(Statement::from_sql_and_values(
+                    DatabaseBackend::Sqlite,
+                    &sql,
+                    values,
+                ))
+                .await?;
+            projections.push(BulkReassignTableDto {
+                table: projection.to_string(),
+                rows: result.rows_affected(),
+            });
+        }
+        events.extend(registrators.into_iter().map(|registrator| {
+            ChangeEvent::projection(PROJECTION_CHANGE_TYPE, registrator, ChangeOp::Updated)
+        }));
+    }
+
+    let mut json_args = Vec::new();
+    let mut set_values: Vec<Value> = Vec::new();
+    if let Some(org) = &organization_id {
+        json_args.push("'$.organization_id', ?");
+        set_values.push(org.clone().into());
+    }
+    if let Some(mp) = &marketplace_id {
+        json_args.push("'$.marketplace_id', ?");
+        set_values.push(mp.clone().into());
+    }
+    let mut assignments = vec![format!(
+        "header_json = json_set(header_json, {})",
+        json_args.join(", ")
+    )];
+    if let (true, Some(org)) = (spec.organization_column, &organization_id) {
+        assignments.push("organization_id = ?".to_string());
+        set_values.push(org.clone().into());
+    }
+    assignments.push("updated_at = ?".to_string());
+    set_values.push(Utc::now().into());
+    assignments.push("version = version + 1".to_string());
+
+    let (changed_sql, changed_values) =
+        changed_condition(organization_id.as_deref(), marketplace_id.as_deref());
+    let mut filter_values = where_values;
+    filter_values.extend(changed_values);
+    set_values.extend(filter_values.iter().cloned());
+    let mut documents_changed = 0usize;
+    for document_table in projection_archive::physical_tables(table) {
+        // Id — до обновления: после него условие «значение изменится» уже ложно
+        let ids_sql =
+            format!("SELECT id FROM {document_table} WHERE {where_sql} AND {changed_sql}");
+        let rows = txn
+            .query_all(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                &ids_sql,
+                filter_values.clone(),
+            ))
+            .await?;
+        for row in rows {
+            let id: String = row.try_get("", "id")?;
+            events.push(ChangeEvent::aggregate(table, id, ChangeOp::Updated));
+        }
+        let sql = format!(
+            "UPDATE {document_table} SET {} WHERE {where_sql} AND {changed_sql}",
+            assignments.join(", ")
+        );
+        documents_changed += txn
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                &sql,
+                set_values.clone(),
+            ))
+            .await?
+            .rows_affected() as usize;
+    }
+    cdc::record_with_conn(&txn, &events).await?;
+
+    if req.dry_run {
+        txn.rollback().await?;
+    } else {
+        txn.commit().await?;
+        tracing::info!(
+            "Bulk reassign {} by {}: matched {}, changed {}, organization {:?}, marketplace {:?}, projections {:?}",
+            table,
+            user_id,
+            matched,
+            documents_changed,
+            organization_id,
+            marketplace_id,
+            projections
+                .iter()
+                .map(|p| (p.table.as_str(), p.rows))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    Ok(BulkReassignReportDto {
+        dry_run: req.dry_run,
+        entity_type: table.to_string(),
+        matched,
+        documents_changed,
+        projections,
+        sample,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_filter_is_rejected() {
+        let err = build_filter(&BulkReassignFilter::default()).unwrap_err();
+        assert!(err.to_string().starts_with("Invalid filter"));
+
+        let blank = BulkReassignFilter {
+            ids: vec!["  ".to_string()],
+            organization_id: Some(" ".to_string()),
+            ..Default::default()
+        };
+        assert!(build_filter(&blank).is_err());
+    }
+
+    #[test]
+    fn filter_builds_conditions_in_placeholder_order() {
+        let filter = BulkReassignFilter {
+            ids: vec!["a".to_string(), "b".to_string()],
+            organization_id: Some("org-old".to_string()),
+            connection_id: Some("conn-1".to_string()),
+            created_to: Some("2025-03-31".to_string()),
+            ..Default::default()
+        };
+        let (sql, values) = build_filter(&filter).unwrap();
+        assert_eq!(
+            sql,
+            "is_deleted = 0 AND id IN (?, ?) \
+             AND json_extract(header_json, '$.organization_id') = ? \
+             AND json_extract(header_json, '$.connection_id') = ? \
+             AND substr(created_at, 1, 10) <= ?"
+        );
+        assert_eq!(values.len(), 5);
+        assert_eq!(values[2], Value::from("org-old".to_string()));
+        assert_eq!(values[4], Value::from("2025-03-31".to_string()));
+    }
+
+    #[test]
+    fn invalid_period_is_rejected() {
+        let bad_date = BulkReassignFilter {
+            created_from: Some("31.03.2025".to_string()),
+            ..Default::default()
+        };
+        assert!(build_filter(&bad_date).is_err());
+
+        let reversed = BulkReassignFilter {
+            created_from: Some("2025-04-01".to_string()),
+            created_to: Some("2025-03-01".to_string()),
+            ..Default::default()
+        };
+        assert!(build_filter(&reversed).is_err());
+    }
+
+    #[test]
+    fn unsupported_entity_type_is_rejected() {
+        assert!(spec("a010_ozon_fbs_posting").is_err());
+        assert!(spec("a015_wb_orders").is_ok_and(|s| !s.organization_column));
+    }
+}
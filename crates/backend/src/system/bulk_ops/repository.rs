@@ -0,0 +1,107 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, EntityTrait, QueryOrder, QuerySelect, Set, Statement,
+};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sys_bulk_operations")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub op_kind: String,
+    pub entity_type: String,
+    pub created_at: String,
+    pub created_by: Option<String>,
+    pub item_count: i64,
+    pub snapshot_json: String,
+    pub status: String,
+    pub undone_at: Option<String>,
+    pub undone_by: Option<String>,
+    pub undo_error: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+fn conn() -> &'static DatabaseConnection {
+    get_connection()
+}
+
+pub async fn insert(model: Model) -> Result<(), DbErr> {
+    ActiveModel {
+        id: Set(model.id),
+        op_kind: Set(model.op_kind),
+        entity_type: Set(model.entity_type),
+        created_at: Set(model.created_at),
+        created_by: Set(model.created_by),
+        item_count: Set(model.item_count),
+        snapshot_json: Set(model.snapshot_json),
+        status: Set(model.status),
+        undone_at: Set(model.undone_at),
+        undone_by: Set(model.undone_by),
+        undo_error: Set(model.undo_error),
+    }
+    .insert(conn())
+    .await?;
+    Ok(())
+}
+
+pub async fn list_recent(limit: u64) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .order_by_desc(Column::CreatedAt)
+        .limit(limit)
+        .all(conn())
+        .await
+}
+
+pub async fn get_by_id(id: &str) -> Result<Option<Model>, DbErr> {
+    Entity::find_by_id(id.to_string()).one(conn()).await
+}
+
+/// Атомарно переводит операцию `from` → `to`; `false`, если статус уже другой
+/// (защита от двойной отмены параллельными запросами).
+pub async fn claim_status(id: &str, from: &str, to: &str) -> Result<bool, DbErr> {
+    let result = conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_bulk_operations SET status = ?1 WHERE id = ?2 AND status = ?3",
+            vec![to.into(), id.into(), from.into()],
+        ))
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Завершает отмену в транзакции перепроведения — статус меняется вместе с документами.
+pub async fn finish_undo_with_conn<C: ConnectionTrait>(
+    db: &C,
+    id: &str,
+    status: &str,
+    undone_at: &str,
+    undone_by: &str,
+) -> Result<(), DbErr> {
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "UPDATE sys_bulk_operations \
+         SET status = ?1, undone_at = ?2, undone_by = ?3, undo_error = NULL \
+         WHERE id = ?4",
+        vec![status.into(), undone_at.into(), undone_by.into(), id.into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Возвращает операцию из `from` в `to` после неудачной отмены и сохраняет ошибку.
+pub async fn release_undo(id: &str, from: &str, to: &str, undo_error: &str) -> Result<(), DbErr> {
+    conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_bulk_operations SET status = ?1, undo_error = ?2 WHERE id = ?3 AND status = ?4",
+            vec![to.into(), undo_error.into(), id.into(), from.into()],
+        ))
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,288 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use contracts::system::bulk_ops::{BulkOperationDto, BulkOperationListResponse, BulkUndoResultDto};
+use uuid::Uuid;
+
+use super::repository;
+use crate::shared::data::unit_of_work::UnitOfWork;
+
+pub const OP_UNPOST: &str = "unpost";
+
+const STATUS_DONE: &str = "done";
+const STATUS_UNDOING: &str = "undoing";
+const STATUS_UNDONE: &str = "undone";
+
+const LIST_DEFAULT_LIMIT: u64 = 200;
+const LIST_MAX_LIMIT: u64 = 1000;
+
+/// Был ли документ проведён — снимок прежнего состояния перед пакетной отменой проведения.
+pub async fn is_posted(entity_type: &str, id: Uuid) -> Result<bool> {
+    let posted = match entity_type {
+        "a012_wb_sales" => crate::domain::a012_wb_sales::repository::get_by_id(id)
+            .await?
+            .map(|d| d.is_posted),
+        "a013_ym_order" => crate::domain::a013_ym_order::repository::get_by_id(id)
+            .await?
+            .map(|d| d.is_posted),
+        "a016_ym_returns" => crate::domain::a016_ym_returns::repository::get_by_id(id)
+            .await?
+            .map(|d| d.is_posted),
+        other => bail!("Bulk undo is not supported for '{other}'"),
+    };
+    Ok(posted.unwrap_or(false))
+}
+
+/// Проведение одного документа (одна транзакция на документ).
+pub async fn post_document(entity_type: &str, id: Uuid) -> Result<()> {
+    match entity_type {
+        "a012_wb_sales" => crate::domain::a012_wb_sales::posting::post_document(id).await,
+        "a013_ym_order" => crate::domain::a013_ym_order::posting::post_document(id).await,
+        "a016_ym_returns" => crate::domain::a016_ym_returns::posting::post_document(id).await,
+        other => bail!("Bulk posting is not supported for '{other}'"),
+    }
+}
+
+/// Отмена проведения одного документа.
+pub async fn unpost_document(entity_type: &str, id: Uuid) -> Result<()> {
+    match entity_type {
+        "a012_wb_sales" => crate::domain::a012_wb_sales::posting::unpost_document(id).await,
+        "a013_ym_order" => crate::domain::a013_ym_order::posting::unpost_document(id).await,
+        "a016_ym_returns" => crate::domain::a016_ym_returns::posting::unpost_document(id).await,
+        other => bail!("Bulk unposting is not supported for '{other}'"),
+    }
+}
+
+/// Записывает пакетную отмену проведения; `ids` — документы, которые были проведены
+/// до операции и успешно распроведены. Пустой список не журналируется.
+pub async fn record_unpost(entity_type: &str, ids: &[Uuid], user_id: &str) -> Result<()> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let snapshot: Vec<String> = ids.iter().map(Uuid::to_string).collect();
+    repository::insert(repository::Model {
+        id: Uuid::new_v4().to_string(),
+        op_kind: OP_UNPOST.to_string(),
+        entity_type: entity_type.to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        created_by: Some(user_id.to_string()),
+        item_count: ids.len() as i64,
+        snapshot_json: serde_json::to_string(&snapshot)?,
+        status: STATUS_DONE.to_string(),
+        undone_at: None,
+        undone_by: None,
+        undo_error: None,
+    })
+    .await?;
+    Ok(())
+}
+
+fn undo_deadline(created_at: DateTime<Utc>, window_minutes: i64) -> DateTime<Utc> {
+    created_at + Duration::minutes(window_minutes)
+}
+
+fn can_undo(
+    status: &str,
+    created_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+    window_minutes: i64,
+) -> bool {
+    status == STATUS_DONE && now < undo_deadline(created_at, window_minutes)
+}
+
+fn parse_ts(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn to_dto(model: repository::Model, window_minutes: i64, now: DateTime<Utc>) -> BulkOperationDto {
+    let created_at = parse_ts(&model.created_at);
+    BulkOperationDto {
+        undo_deadline: created_at
+            .map(|ts| undo_deadline(ts, window_minutes).to_rfc3339())
+            .unwrap_or_default(),
+        can_undo: created_at
+            .map(|ts| can_undo(&model.status, ts, now, window_minutes))
+            .unwrap_or(false),
+        id: model.id,
+        op_kind: model.op_kind,
+        entity_type: model.entity_type,
+        created_at: model.created_at,
+        created_by: model.created_by,
+        item_count: model.item_count,
+        status: model.status,
+        undone_at: model.undone_at,
+        undone_by: model.undone_by,
+        undo_error: model.undo_error,
+    }
+}
+
+pub async fn list(limit: Option<u64>) -> Result<BulkOperationListResponse> {
+    let limit = limit.unwrap_or(LIST_DEFAULT_LIMIT).clamp(1, LIST_MAX_LIMIT);
+    let window_minutes = crate::system::settings::service::get_bulk_undo_window_minutes().await?;
+    let now = Utc::now();
+    let rows = repository::list_recent(limit)
+        .await?
+        .into_iter()
+        .map(|m| to_dto(m, window_minutes, now))
+        .collect();
+    Ok(BulkOperationListResponse {
+        rows,
+        undo_window_minutes: window_minutes,
+    })
+}
+
+pub async fn get(id: &str) -> Result<Option<BulkOperationDto>> {
+    let window_minutes = crate::system::settings::service::get_bulk_undo_window_minutes().await?;
+    Ok(repository::get_by_id(id)
+        .await?
+        .map(|m| to_dto(m, window_minutes, Utc::now())))
+}
+
+/// Перепроведение из снимка, собранное до транзакции отмены.
+enum PreparedRepost {
+    WbSales(crate::domain::a012_wb_sales::posting::PreparedPosting),
+    YmOrder(crate::domain::a013_ym_order::posting::PreparedPosting),
+    YmReturn(crate::domain::a016_ym_returns::posting::PreparedPosting),
+}
+
+async fn prepare_repost(
+    entity_type: &str,
+    id: Uuid,
+    cache: &mut crate::domain::a012_wb_sales::service::PostingPreparationCache,
+) -> Result<PreparedRepost> {
+    Ok(match entity_type {
+        "a012_wb_sales" => PreparedRepost::WbSales(
+            crate::domain::a012_wb_sales::posting::prepare_posting(id, cache).await?,
+        ),
+        "a013_ym_order" => PreparedRepost::YmOrder(
+            crate::domain::a013_ym_order::posting::prepare_posting(id).await?,
+        ),
+        "a016_ym_returns" => PreparedRepost::YmReturn(
+            crate::domain::a016_ym_returns::posting::prepare_posting(id).await?,
+        ),
+        other => bail!("Bulk undo is not supported for '{other}'"),
+    })
+}
+
+async fn write_repost(uow: &mut UnitOfWork, prepared: &PreparedRepost) -> Result<()> {
+    match prepared {
+        PreparedRepost::WbSales(p) => {
+            crate::domain::a012_wb_sales::posting::write_posting(uow, p).await
+        }
+        PreparedRepost::YmOrder(p) => {
+            crate::domain::a013_ym_order::posting::write_posting(uow, p).await
+        }
+        PreparedRepost::YmReturn(p) => {
+            crate::domain::a016_ym_returns::posting::write_posting(uow, p).await
+        }
+    }
+}
+
+/// Перепроводит все документы снимка и отмечает операцию отменённой одной транзакцией:
+/// либо восстановлено всё, либо ничего. Подготовка (чтения, автозаполнение ссылок)
+/// идёт до транзакции, чтобы не держать write-lock SQLite.
+async fn restore_snapshot(model: &repository::Model, user_id: &str) -> Result<usize> {
+    let ids: Vec<String> = serde_json::from_str(&model.snapshot_json)?;
+    let mut cache = crate::domain::a012_wb_sales::service::PostingPreparationCache::default();
+    let mut prepared = Vec::with_capacity(ids.len());
+    for id_str in &ids {
+        let id = Uuid::parse_str(id_str).with_context(|| id_str.clone())?;
+        let repost = prepare_repost(&model.entity_type, id, &mut cache)
+            .await
+            .with_context(|| id_str.clone())?;
+        prepared.push(repost);
+    }
+
+    let mut uow = UnitOfWork::begin().await?;
+    for repost in &prepared {
+        write_repost(&mut uow, repost).await?;
+    }
+    repository::finish_undo_with_conn(
+        uow.conn(),
+        &model.id,
+        STATUS_UNDONE,
+        &Utc::now().to_rfc3339(),
+        user_id,
+    )
+    .await?;
+    uow.commit().await?;
+    Ok(prepared.len())
+}
+
+/// Отменяет операцию: перепроводит документы из снимка. `None` — отмена недоступна
+/// (окно истекло, операция уже отменена или отменяется параллельно). Отмена
+/// всё-или-ничего: при ошибке операция возвращается в «Выполнена» с текстом ошибки.
+/// Поддерживается только пакетная отмена проведения ([`OP_UNPOST`]); для других видов
+/// операций снимок не пишется, и отмена отклоняется.
+pub async fn undo(id: &str, user_id: &str) -> Result<Option<BulkUndoResultDto>> {
+    let Some(op) = get(id).await? else {
+        return Ok(None);
+    };
+    if op.op_kind != OP_UNPOST {
+        bail!("Undo is not supported for '{}' operations", op.op_kind);
+    }
+    if !op.can_undo || !repository::claim_status(id, STATUS_DONE, STATUS_UNDOING).await? {
+        return Ok(None);
+    }
+    let Some(model) = repository::get_by_id(id).await? else {
+        return Ok(None);
+    };
+
+    let restored = match restore_snapshot(&model, user_id).await {
+        Ok(restored) => restored,
+        Err(e) => {
+            tracing::warn!("Bulk undo {} failed, nothing restored: {:#}", id, e);
+            repository::release_undo(id, STATUS_UNDOING, STATUS_DONE, &format!("{e:#}")).await?;
+            return Err(e);
+        }
+    };
+
+    tracing::info!(
+        "Bulk undo {} ({} {}): restored {}",
+        id,
+        model.op_kind,
+        model.entity_type,
+        restored
+    );
+
+    Ok(Some(BulkUndoResultDto {
+        restored,
+        status: STATUS_UNDONE.to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(value: &str) -> DateTime<Utc> {
+        parse_ts(value).unwrap()
+    }
+
+    #[test]
+    fn undo_allowed_only_inside_window() {
+        let created = ts("2026-10-17T10:00:00+00:00");
+        assert!(can_undo(
+            STATUS_DONE,
+            created,
+            ts("2026-10-17T10:59:59+00:00"),
+            60
+        ));
+        assert!(!can_undo(
+            STATUS_DONE,
+            created,
+            ts("2026-10-17T11:00:00+00:00"),
+            60
+        ));
+    }
+
+    #[test]
+    fn undo_not_allowed_after_undo_or_with_zero_window() {
+        let created = ts("2026-10-17T10:00:00+00:00");
+        let now = ts("2026-10-17T10:05:00+00:00");
+        assert!(!can_undo(STATUS_UNDONE, created, now, 60));
+        assert!(!can_undo(STATUS_UNDOING, created, now, 60));
+        assert!(!can_undo(STATUS_DONE, created, now, 0));
+    }
+}
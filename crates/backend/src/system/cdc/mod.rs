@@ -0,0 +1,13 @@
+//! Лента изменений (CDC) для внешних потребителей — корпоративного DWH.
+//!
+//! Вместо ночной полной перевыгрузки DWH забирает события по offset:
+//! `GET /api/ext/v1/changes?after=<последний offset>`. Событие говорит только
+//! «что и как изменилось» (тип сущности, id, операция); сами данные потребитель
+//! дочитывает существующими выгрузками. Для проекций `entity_id` — registrator_ref:
+//! строки документа в проекции заменены (`posted`) или удалены (`unposted`).
+//!
+//! События пишутся в той же транзакции, что и данные, поэтому лента не расходится
+//! с таблицами. Сейчас источники — WB продажи (a012) с проекциями p900/p904 и WB заказы (a015).
+
+pub mod repository;
+pub mod service;
@@ -0,0 +1,93 @@
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr, FromQueryResult, Statement, Value};
+
+use crate::shared::data::db::get_connection;
+
+use super::service::ChangeEvent;
+
+fn conn() -> &'static DatabaseConnection {
+    get_connection()
+}
+
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct ChangeRow {
+    pub change_offset: i64,
+    pub ts: String,
+    pub entity_kind: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub op: String,
+}
+
+/// Дописывает события одной пачкой; `db` — транзакция вызывающего.
+pub async fn append_with_conn<C: ConnectionTrait>(
+    db: &C,
+    ts: &str,
+    events: &[ChangeEvent],
+) -> Result<(), DbErr> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let placeholders = vec!["(?, ?, ?, ?, ?)"; events.len()].join(", ");
+    let sql = format!(
+        "INSERT INTO sys_change_log (ts, entity_kind, entity_type, entity_id, op) \
+         VALUES {placeholders}"
+    );
+    let mut values: Vec<Value> = Vec::with_capacity(events.len() * 5);
+    for event in events {
+        values.push(ts.into());
+        values.push(event.kind.as_str().into());
+        values.push(event.entity_type.into());
+        values.push(event.entity_id.clone().into());
+        values.push(event.op.as_str().into());
+    }
+    db.execute(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        sql,
+        values,
+    ))
+    .await?;
+    Ok(())
+}
+
+pub async fn list_after(after: i64, limit: u64) -> Result<Vec<ChangeRow>, DbErr> {
+    let stmt = Statement::from_sql_and_values(
+        sea_orm::DatabaseBackend::Sqlite,
+        "SELECT change_offset, ts, entity_kind, entity_type, entity_id, op \
+         FROM sys_change_log \
+         WHERE change_offset > ? \
+         ORDER BY change_offset \
+         LIMIT ?",
+        vec![after.into(), (limit as i64).into()],
+    );
+    let rows = conn().query_all(stmt).await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|r| ChangeRow::from_query_result(&r, "").ok())
+        .collect())
+}
+
+/// Наименьший хранимый offset (`None`, если лента пуста) — по нему потребитель
+/// понимает, что его позиция вычищена ретеншном и нужна полная перевыгрузка.
+pub async fn min_offset() -> Result<Option<i64>, DbErr> {
+    let row = conn()
+        .query_one(Statement::from_string(
+            sea_orm::DatabaseBackend::Sqlite,
+            "SELECT MIN(change_offset) AS min_offset FROM sys_change_log".to_string(),
+        ))
+        .await?;
+    match row {
+        Some(row) => row.try_get::<Option<i64>>("", "min_offset"),
+        None => Ok(None),
+    }
+}
+
+pub async fn prune_older_than(days: i64) -> Result<u64, DbErr> {
+    let res = conn()
+        .execute(Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            "DELETE FROM sys_change_log WHERE datetime(ts) < datetime('now', ?)",
+            vec![format!("-{days} days").into()],
+        ))
+        .await?;
+    Ok(res.rows_affected())
+}
@@ -0,0 +1,200 @@
+use anyhow::Result;
+use chrono::Utc;
+use sea_orm::ConnectionTrait;
+
+use super::repository::{self, ChangeRow};
+
+/// Сколько суток храним ленту. DWH забирает её минимум раз в сутки; отставший
+/// дальше потребитель видит это по `min_offset` и делает полную перевыгрузку.
+pub const RETENTION_DAYS: i64 = 30;
+
+/// Потолок событий в одном ответе.
+pub const MAX_LIMIT: u64 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Aggregate,
+    Projection,
+}
+
+impl ChangeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChangeKind::Aggregate => "aggregate",
+            ChangeKind::Projection => "projection",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeOp {
+    Created,
+    Updated,
+    Posted,
+    Unposted,
+    /// Пометка удаления агрегата
+    Deleted,
+}
+
+impl ChangeOp {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChangeOp::Created => "created",
+            ChangeOp::Updated => "updated",
+            ChangeOp::Posted => "posted",
+            ChangeOp::Unposted => "unposted",
+            ChangeOp::Deleted => "deleted",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChangeEvent {
+    pub kind: ChangeKind,
+    pub entity_type: &'static str,
+    pub entity_id: String,
+    pub op: ChangeOp,
+}
+
+impl ChangeEvent {
+    pub fn aggregate(
+        entity_type: &'static str,
+        entity_id: impl Into<String>,
+        op: ChangeOp,
+    ) -> Self {
+        Self {
+            kind: ChangeKind::Aggregate,
+            entity_type,
+            entity_id: entity_id.into(),
+            op,
+        }
+    }
+
+    pub fn projection(
+        entity_type: &'static str,
+        registrator_ref: impl Into<String>,
+        op: ChangeOp,
+    ) -> Self {
+        Self {
+            kind: ChangeKind::Projection,
+            entity_type,
+            entity_id: registrator_ref.into(),
+            op,
+        }
+    }
+}
+
+/// События проведения/распроведения документа: сам агрегат и каждая проекция,
+/// в которой заменены (удалены) строки этого регистратора.
+pub fn posting_events(
+    aggregate_type: &'static str,
+    id: &str,
+    projections: &[&'static str],
+    op: ChangeOp,
+) -> Vec<ChangeEvent> {
+    std::iter::once(ChangeEvent::aggregate(aggregate_type, id, op))
+        .chain(
+            projections
+                .iter()
+                .map(|projection| ChangeEvent::projection(projection, id, op)),
+        )
+        .collect()
+}
+
+/// Запись событий внутри транзакции вызывающего.
+pub async fn record_with_conn<C: ConnectionTrait>(db: &C, events: &[ChangeEvent]) -> Result<()> {
+    repository::append_with_conn(db, &now_ts(), events)
+        .await
+        .map_err(|e| anyhow::anyhow!("Database error: {}", e))
+}
+
+/// Страница ленты после `after`.
+pub struct ChangesPage {
+    pub items: Vec<ChangeRow>,
+    /// Offset, с которого продолжать (`after` следующего запроса).
+    pub next_offset: i64,
+    pub has_more: bool,
+    pub min_offset: Option<i64>,
+}
+
+pub async fn list_changes(after: i64, limit: u64) -> Result<ChangesPage> {
+    let limit = limit.clamp(1, MAX_LIMIT);
+    // +1 строка, чтобы без COUNT понять, есть ли продолжение.
+    let mut items = repository::list_after(after, limit + 1)
+        .await
+        .map_err(|e| anyhow::anyhow!("Database error: {}", e))?;
+    let has_more = items.len() as u64 > limit;
+    items.truncate(limit as usize);
+    let min_offset = repository::min_offset()
+        .await
+        .map_err(|e| anyhow::anyhow!("Database error: {}", e))?;
+    Ok(ChangesPage {
+        next_offset: next_offset(after, &items),
+        has_more,
+        items,
+        min_offset,
+    })
+}
+
+fn next_offset(after: i64, items: &[ChangeRow]) -> i64 {
+    items.last().map(|row| row.change_offset).unwrap_or(after)
+}
+
+pub async fn prune_old() -> Result<u64> {
+    repository::prune_older_than(RETENTION_DAYS)
+        .await
+        .map_err(|e| anyhow::anyhow!("Database error: {}", e))
+}
+
+/// Суточный цикл прунинга, запускается из `main.rs` рядом с прунингом ext_api_log.
+pub async fn run_prune_loop() {
+    loop {
+        match prune_old().await {
+            Ok(n) if n > 0 => {
+                tracing::info!("[cdc] pruned {n} change events older than {RETENTION_DAYS} days")
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("[cdc] prune failed: {e}"),
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(24 * 60 * 60)).await;
+    }
+}
+
+fn now_ts() -> String {
+    Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posting_events_cover_aggregate_and_projections() {
+        let events = posting_events(
+            "a012_wb_sales",
+            "doc-1",
+            &["p900_mp_sales_register", "p904_sales_data"],
+            ChangeOp::Posted,
+        );
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].kind, ChangeKind::Aggregate);
+        assert_eq!(events[0].entity_type, "a012_wb_sales");
+        assert!(events[1..]
+            .iter()
+            .all(|e| e.kind == ChangeKind::Projection && e.entity_id == "doc-1"));
+    }
+
+    #[test]
+    fn next_offset_keeps_position_on_empty_page() {
+        let row = ChangeRow {
+            change_offset: 42,
+            ts: String::new(),
+            entity_kind: "aggregate".to_string(),
+            entity_type: "a015_wb_orders".to_string(),
+            entity_id: "x".to_string(),
+            op: "updated".to_string(),
+        };
+        assert_eq!(next_offset(10, &[]), 10);
+        assert_eq!(next_offset(10, &[row]), 42);
+    }
+}
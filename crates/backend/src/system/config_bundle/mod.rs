@@ -0,0 +1,8 @@
+//! Экспорт/импорт пакета пользовательской конфигурации (`/api/sys/config-bundle`).
+//!
+//! Импорт только добавляет и обновляет записи по их ключам (form_key, id дашборда,
+//! ключ настройки, организация, username) и ничего не удаляет, поэтому пакет можно
+//! применять повторно.
+
+pub mod repository;
+pub mod service;
@@ -0,0 +1,153 @@
+use anyhow::Result;
+use chrono::Utc;
+use contracts::shared::form_settings::FormSettings;
+use contracts::system::config_bundle::BundleDashboardConfig;
+use contracts::system::favorites::FavoriteUpsertRequest;
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+use crate::shared::data::db::get_connection;
+
+pub async fn list_form_settings() -> Result<Vec<FormSettings>> {
+    let rows = get_connection()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT form_key, settings_json FROM user_form_settings ORDER BY form_key",
+            vec![],
+        ))
+        .await?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            Some(FormSettings {
+                form_key: row.try_get("", "form_key").ok()?,
+                settings_json: row.try_get("", "settings_json").ok()?,
+            })
+        })
+        .collect())
+}
+
+pub async fn upsert_form_settings(item: &FormSettings) -> Result<()> {
+    get_connection()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO user_form_settings (form_key, settings_json, updated_at) VALUES (?, ?, ?)
+             ON CONFLICT(form_key) DO UPDATE SET
+                 settings_json = excluded.settings_json,
+                 updated_at = excluded.updated_at",
+            [
+                item.form_key.clone().into(),
+                item.settings_json.clone().into(),
+                Utc::now().to_rfc3339().into(),
+            ],
+        ))
+        .await?;
+    Ok(())
+}
+
+pub async fn list_dashboard_configs() -> Result<Vec<BundleDashboardConfig>> {
+    let rows = get_connection()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT id, name, description, data_source, config_json
+             FROM sys_dashboard_configs ORDER BY name",
+            vec![],
+        ))
+        .await?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            Some(BundleDashboardConfig {
+                id: row.try_get("", "id").ok()?,
+                name: row.try_get("", "name").ok()?,
+                description: row.try_get("", "description").ok()?,
+                data_source: row.try_get("", "data_source").ok()?,
+                config_json: row.try_get("", "config_json").ok()?,
+            })
+        })
+        .collect())
+}
+
+/// Вставка или замена по id (повторный импорт того же пакета не плодит копии).
+pub async fn upsert_dashboard_config(item: &BundleDashboardConfig) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    get_connection()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO sys_dashboard_configs
+                 (id, name, description, data_source, config_json, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET
+                 name = excluded.name,
+                 description = excluded.description,
+                 data_source = excluded.data_source,
+                 config_json = excluded.config_json,
+                 updated_at = excluded.updated_at",
+            [
+                item.id.clone().into(),
+                item.name.clone().into(),
+                item.description.clone().into(),
+                item.data_source.clone().into(),
+                item.config_json.clone().into(),
+                now.clone().into(),
+                now.into(),
+            ],
+        ))
+        .await?;
+    Ok(())
+}
+
+/// Избранное всех пользователей: (username владельца, запись).
+pub async fn list_favorites() -> Result<Vec<(String, FavoriteUpsertRequest)>> {
+    let rows = get_connection()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT u.username, f.target_kind, f.target_id, f.target_title, f.tab_key,
+                    f.color, f.comment, f.is_global
+             FROM sys_favorites f
+             JOIN sys_users u ON u.id = f.owner_user_id
+             ORDER BY u.username, f.created_at",
+            vec![],
+        ))
+        .await?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let is_global: i32 = row.try_get("", "is_global").ok()?;
+            Some((
+                row.try_get("", "username").ok()?,
+                FavoriteUpsertRequest {
+                    target_kind: row.try_get("", "target_kind").ok()?,
+                    target_id: row.try_get("", "target_id").ok()?,
+                    target_title: row.try_get("", "target_title").ok()?,
+                    tab_key: row.try_get("", "tab_key").ok()?,
+                    color: row.try_get("", "color").ok()?,
+                    comment: row.try_get("", "comment").ok()?,
+                    is_global: is_global != 0,
+                },
+            ))
+        })
+        .collect())
+}
+
+/// Пользователи, у которых сохранены настройки уведомлений: (id, username).
+pub async fn list_users_with_notification_preferences() -> Result<Vec<(String, String)>> {
+    let rows = get_connection()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT DISTINCT u.id, u.username
+             FROM sys_notification_preferences p
+             JOIN sys_users u ON u.id = p.user_id
+             ORDER BY u.username",
+            vec![],
+        ))
+        .await?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            Some((
+                row.try_get("", "id").ok()?,
+                row.try_get("", "username").ok()?,
+            ))
+        })
+        .collect())
+}
@@ -0,0 +1,331 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use contracts::projections::p904_sales_data::dto::ReturnNettingMode;
+use contracts::system::branding::OrgBrandingUpsertRequest;
+use contracts::system::config_bundle::{
+    BundleFavorite, BundleNotificationPreferences, BundleOrgBranding, BundleSetting, ConfigBundle,
+    ConfigImportResult, ConfigImportSection, CONFIG_BUNDLE_FORMAT_VERSION,
+    CONFIG_BUNDLE_SETTING_KEYS,
+};
+
+use super::repository;
+use crate::system::{branding, favorites, notifications, settings, users};
+
+/// Собирает пакет из текущего состояния базы.
+pub async fn export() -> Result<ConfigBundle> {
+    let mut setting_values = Vec::new();
+    for key in CONFIG_BUNDLE_SETTING_KEYS {
+        if let Some(value) = settings::repository::get_setting(key).await? {
+            setting_values.push(BundleSetting {
+                key: key.to_string(),
+                value,
+            });
+        }
+    }
+
+    // Хосты относятся к конкретному серверу и в пакет не попадают
+    let org_branding = branding::service::list()
+        .await?
+        .into_iter()
+        .filter_map(|org| {
+            let mut settings = org.settings?;
+            settings.hosts.clear();
+            Some(BundleOrgBranding {
+                organization_id: org.organization_id,
+                settings,
+            })
+        })
+        .collect();
+
+    let favorites = repository::list_favorites()
+        .await?
+        .into_iter()
+        .map(|(username, favorite)| BundleFavorite { username, favorite })
+        .collect();
+
+    let mut notification_preferences = Vec::new();
+    for (user_id, username) in repository::list_users_with_notification_preferences().await? {
+        notification_preferences.push(BundleNotificationPreferences {
+            username,
+            preferences: notifications::repository::list_preferences(&user_id).await?,
+        });
+    }
+
+    Ok(ConfigBundle {
+        format_version: CONFIG_BUNDLE_FORMAT_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        form_settings: repository::list_form_settings().await?,
+        dashboard_configs: repository::list_dashboard_configs().await?,
+        settings: setting_values,
+        org_branding,
+        favorites,
+        notification_preferences,
+    })
+}
+
+fn validate_setting(setting: &BundleSetting) -> Result<()> {
+    let valid = match setting.key.as_str() {
+        "p904_return_netting_mode" => ReturnNettingMode::from_code(&setting.value).is_some(),
+        "bulk_undo_window_minutes" => setting.value.trim().parse::<i64>().is_ok(),
+        _ => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Invalid bundle: setting {} = {:?} is not supported",
+            setting.key,
+            setting.value
+        ))
+    }
+}
+
+/// Проверка пакета целиком до применения: пакет либо применяется, либо отклоняется.
+pub fn validate(bundle: &ConfigBundle) -> Result<()> {
+    if bundle.format_version != CONFIG_BUNDLE_FORMAT_VERSION {
+        return Err(anyhow!(
+            "Invalid bundle: format version {} is not supported (expected {})",
+            bundle.format_version,
+            CONFIG_BUNDLE_FORMAT_VERSION
+        ));
+    }
+    for item in &bundle.form_settings {
+        if item.form_key.trim().is_empty() {
+            return Err(anyhow!("Invalid bundle: empty form key"));
+        }
+        serde_json::from_str::<serde_json::Value>(&item.settings_json).map_err(|e| {
+            anyhow!(
+                "Invalid bundle: form {} settings are not JSON: {}",
+                item.form_key,
+                e
+            )
+        })?;
+    }
+    for item in &bundle.dashboard_configs {
+        if item.id.trim().is_empty() || item.name.trim().is_empty() {
+            return Err(anyhow!(
+                "Invalid bundle: dashboard config without id or name"
+            ));
+        }
+        serde_json::from_str::<serde_json::Value>(&item.config_json).map_err(|e| {
+            anyhow!(
+                "Invalid bundle: dashboard {} config is not JSON: {}",
+                item.name,
+                e
+            )
+        })?;
+    }
+    for setting in &bundle.settings {
+        validate_setting(setting)?;
+    }
+    Ok(())
+}
+
+fn section(name: &str, applied: usize, skipped: usize) -> ConfigImportSection {
+    ConfigImportSection {
+        section: name.to_string(),
+        applied,
+        skipped,
+    }
+}
+
+/// Id пользователя целевого развёртывания по `username` (кэшируется на время импорта).
+async fn resolve_user(
+    cache: &mut std::collections::HashMap<String, Option<String>>,
+    username: &str,
+) -> Result<Option<String>> {
+    if let Some(id) = cache.get(username) {
+        return Ok(id.clone());
+    }
+    let id = users::repository::get_by_username(username)
+        .await?
+        .map(|user| user.id);
+    cache.insert(username.to_string(), id.clone());
+    Ok(id)
+}
+
+/// Применяет пакет (upsert по ключам; ничего не удаляет). При `dry_run` только
+/// проверяет пакет и сопоставление пользователей/организаций.
+pub async fn import(bundle: ConfigBundle, dry_run: bool) -> Result<ConfigImportResult> {
+    validate(&bundle)?;
+    let mut sections = Vec::new();
+    let mut warnings = Vec::new();
+
+    if !dry_run {
+        for item in &bundle.form_settings {
+            repository::upsert_form_settings(item).await?;
+        }
+        for item in &bundle.dashboard_configs {
+            repository::upsert_dashboard_config(item).await?;
+        }
+        for setting in &bundle.settings {
+            match setting.key.as_str() {
+                "p904_return_netting_mode" => {
+                    if let Some(mode) = ReturnNettingMode::from_code(&setting.value) {
+                        settings::service::set_p904_return_netting_mode(mode).await?;
+                    }
+                }
+                "bulk_undo_window_minutes" => {
+                    let minutes = setting.value.trim().parse::<i64>()?;
+                    settings::service::set_bulk_undo_window_minutes(minutes).await?;
+                }
+                _ => {}
+            }
+        }
+    }
+    sections.push(section("form_settings", bundle.form_settings.len(), 0));
+    sections.push(section(
+        "dashboard_configs",
+        bundle.dashboard_configs.len(),
+        0,
+    ));
+    sections.push(section("settings", bundle.settings.len(), 0));
+
+    let organizations: Vec<String> = branding::repository::list_organizations()
+        .await?
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    let (mut applied, mut skipped) = (0, 0);
+    for item in bundle.org_branding {
+        if !organizations.contains(&item.organization_id) {
+            warnings.push(format!(
+                "Брендирование: организация {} не найдена",
+                item.organization_id
+            ));
+            skipped += 1;
+            continue;
+        }
+        if !dry_run {
+            // Хосты остаются как настроены на этом сервере
+            let mut settings = item.settings;
+            settings.hosts = branding::repository::find(&item.organization_id)
+                .await?
+                .map(|row| {
+                    row.hosts
+                        .split(',')
+                        .filter(|h| !h.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+            if let Err(e) = branding::service::upsert(OrgBrandingUpsertRequest {
+                organization_id: item.organization_id.clone(),
+                settings,
+            })
+            .await
+            {
+                warnings.push(format!(
+                    "Брендирование: организация {}: {}",
+                    item.organization_id, e
+                ));
+                skipped += 1;
+                continue;
+            }
+        }
+        applied += 1;
+    }
+    sections.push(section("org_branding", applied, skipped));
+
+    let mut user_ids = std::collections::HashMap::new();
+    let (mut applied, mut skipped) = (0, 0);
+    for item in bundle.favorites {
+        let Some(user_id) = resolve_user(&mut user_ids, &item.username).await? else {
+            warnings.push(format!(
+                "Избранное: пользователь {} не найден",
+                item.username
+            ));
+            skipped += 1;
+            continue;
+        };
+        if !dry_run {
+            if let Err(e) = favorites::service::upsert(&user_id, item.favorite.clone()).await {
+                warnings.push(format!(
+                    "Избранное {} ({}): {}",
+                    item.favorite.target_title, item.username, e
+                ));
+                skipped += 1;
+                continue;
+            }
+        }
+        applied += 1;
+    }
+    sections.push(section("favorites", applied, skipped));
+
+    let (mut applied, mut skipped) = (0, 0);
+    for item in bundle.notification_preferences {
+        let Some(user_id) = resolve_user(&mut user_ids, &item.username).await? else {
+            warnings.push(format!(
+                "Уведомления: пользователь {} не найден",
+                item.username
+            ));
+            skipped += 1;
+            continue;
+        };
+        if !dry_run {
+            notifications::repository::replace_preferences(&user_id, &item.preferences).await?;
+        }
+        applied += 1;
+    }
+    sections.push(section("notification_preferences", applied, skipped));
+
+    Ok(ConfigImportResult {
+        dry_run,
+        sections,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::shared::form_settings::FormSettings;
+
+    fn bundle() -> ConfigBundle {
+        ConfigBundle {
+            format_version: CONFIG_BUNDLE_FORMAT_VERSION,
+            exported_at: "2026-10-01T00:00:00+00:00".to_string(),
+            form_settings: vec![FormSettings {
+                form_key: "a012_wb_sales_list".to_string(),
+                settings_json: r#"{"columns":["date"]}"#.to_string(),
+            }],
+            dashboard_configs: Vec::new(),
+            settings: vec![BundleSetting {
+                key: "bulk_undo_window_minutes".to_string(),
+                value: "30".to_string(),
+            }],
+            org_branding: Vec::new(),
+            favorites: Vec::new(),
+            notification_preferences: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn validate_accepts_exported_bundle() {
+        assert!(validate(&bundle()).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_other_format_version() {
+        let mut b = bundle();
+        b.format_version = CONFIG_BUNDLE_FORMAT_VERSION + 1;
+        assert!(validate(&b).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_settings_outside_whitelist() {
+        let mut b = bundle();
+        b.settings.push(BundleSetting {
+            key: "jwt_secret".to_string(),
+            value: "x".to_string(),
+        });
+        assert!(validate(&b).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_broken_form_settings_json() {
+        let mut b = bundle();
+        b.form_settings[0].settings_json = "{".to_string();
+        assert!(validate(&b).is_err());
+    }
+}
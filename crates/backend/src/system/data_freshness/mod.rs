@@ -0,0 +1,139 @@
+//! Свежесть данных по источникам для бейджа «данные на …» в списках и дашбордах.
+//!
+//! Время последнего импорта — максимум из успешных запусков регламентных заданий
+//! источника (`sys_task_runs`) и пакетов журнала происхождения (`sys_import_batches`),
+//! куда пишут и ручные загрузки. SLA — из `sys_settings` с запасными значениями
+//! по умолчанию (`FreshnessDataset::default_sla_hours`).
+//!
+//! Ошибка расчёта не должна ломать основной ответ: [`for_datasets`] пишет её в
+//! журнал и возвращает пустой список.
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use contracts::system::data_freshness::{
+    is_stale, DataFreshnessDto, DataFreshnessSlaSettings, FreshnessDataset,
+};
+use sea_orm::{ConnectionTrait, Statement, Value};
+
+use crate::shared::data::db::get_connection;
+use crate::system::settings::service as settings_service;
+
+/// Время из `sys_task_runs` (sqlx пишет `DateTime<Utc>` с пробелом или `T`) и RFC3339.
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f%:z")
+                .map(|dt| dt.with_timezone(&Utc))
+                .ok()
+        })
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+                .map(|dt| dt.and_utc())
+                .ok()
+        })
+}
+
+fn placeholders(n: usize) -> String {
+    vec!["?"; n].join(", ")
+}
+
+async fn max_timestamp(sql: &str, values: Vec<Value>) -> Result<Option<DateTime<Utc>>> {
+    let db = get_connection();
+    let row = db
+        .query_one(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            sql,
+            values,
+        ))
+        .await?;
+    Ok(row
+        .and_then(|r| r.try_get::<Option<String>>("", "last_at").ok().flatten())
+        .and_then(|v| parse_timestamp(&v)))
+}
+
+/// Последний успешный импорт источника.
+pub async fn last_import_at(dataset: FreshnessDataset) -> Result<Option<DateTime<Utc>>> {
+    let mut candidates = Vec::new();
+
+    let task_types = dataset.task_types();
+    if !task_types.is_empty() {
+        let sql = format!(
+            "SELECT MAX(r.finished_at) AS last_at \
+             FROM sys_task_runs r \
+             JOIN sys_tasks t ON t.id = r.task_id \
+             WHERE t.task_type IN ({}) \
+               AND r.status IN ('Completed', 'CompletedWithErrors')",
+            placeholders(task_types.len())
+        );
+        let values = task_types.iter().map(|t| (*t).into()).collect();
+        candidates.push(max_timestamp(&sql, values).await?);
+    }
+
+    let aggregates = dataset.import_aggregates();
+    if !aggregates.is_empty() {
+        let sql = format!(
+            "SELECT MAX(fetched_at) AS last_at FROM sys_import_batches \
+             WHERE aggregate_index IN ({})",
+            placeholders(aggregates.len())
+        );
+        let values = aggregates.iter().map(|a| (*a).into()).collect();
+        candidates.push(max_timestamp(&sql, values).await?);
+    }
+
+    Ok(candidates.into_iter().flatten().max())
+}
+
+fn to_dto(
+    dataset: FreshnessDataset,
+    last_import_at: Option<DateTime<Utc>>,
+    settings: &DataFreshnessSlaSettings,
+    now: DateTime<Utc>,
+) -> DataFreshnessDto {
+    let sla_hours = settings.sla_hours_for(dataset);
+    DataFreshnessDto {
+        dataset,
+        label: dataset.label().to_string(),
+        last_import_at: last_import_at.map(|at| at.to_rfc3339()),
+        sla_hours,
+        stale: is_stale(last_import_at, sla_hours, now),
+    }
+}
+
+/// Свежесть перечисленных источников.
+pub async fn list(datasets: &[FreshnessDataset]) -> Result<Vec<DataFreshnessDto>> {
+    let settings = settings_service::get_data_freshness_sla().await?;
+    let now = Utc::now();
+    let mut result = Vec::with_capacity(datasets.len());
+    for &dataset in datasets {
+        let last = last_import_at(dataset).await?;
+        result.push(to_dto(dataset, last, &settings, now));
+    }
+    Ok(result)
+}
+
+/// Для встраивания в ответы списков и дашбордов: ошибка только в журнал.
+pub async fn for_datasets(datasets: &[FreshnessDataset]) -> Vec<DataFreshnessDto> {
+    list(datasets).await.unwrap_or_else(|e| {
+        tracing::warn!("data freshness: failed to load: {}", e);
+        Vec::new()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_task_run_and_rfc3339_timestamps() {
+        let expected = DateTime::parse_from_rfc3339("2026-10-18T06:32:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(parse_timestamp("2026-10-18T06:32:00.000Z"), Some(expected));
+        assert_eq!(parse_timestamp("2026-10-18 06:32:00+00:00"), Some(expected));
+        assert_eq!(parse_timestamp("2026-10-18 06:32:00"), Some(expected));
+        assert_eq!(parse_timestamp("garbage"), None);
+    }
+}
@@ -0,0 +1,53 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{EntityTrait, Set};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sys_description_templates")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub entity_type: String,
+    pub template: String,
+    pub updated_at: String,
+    pub updated_by: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+fn conn() -> &'static DatabaseConnection {
+    get_connection()
+}
+
+pub async fn list_all() -> Result<Vec<Model>, DbErr> {
+    Entity::find().all(conn()).await
+}
+
+pub async fn upsert(model: Model) -> Result<(), DbErr> {
+    let exists = Entity::find_by_id(model.entity_type.clone())
+        .one(conn())
+        .await?
+        .is_some();
+    let active = ActiveModel {
+        entity_type: Set(model.entity_type),
+        template: Set(model.template),
+        updated_at: Set(model.updated_at),
+        updated_by: Set(model.updated_by),
+    };
+    if exists {
+        active.update(conn()).await?;
+    } else {
+        active.insert(conn()).await?;
+    }
+    Ok(())
+}
+
+pub async fn delete(entity_type: &str) -> Result<u64, DbErr> {
+    Ok(Entity::delete_by_id(entity_type.to_string())
+        .exec(conn())
+        .await?
+        .rows_affected)
+}
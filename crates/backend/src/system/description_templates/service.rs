@@ -0,0 +1,289 @@
+//! Шаблоны описаний документов импорта. Шаблоны кэшируются в памяти: импорт
+//! спрашивает шаблон на каждый документ.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use contracts::domain::a012_wb_sales::aggregate::WbSales;
+use contracts::domain::a015_wb_orders::aggregate::WbOrders;
+use contracts::system::description_templates::{
+    render_template, template_entity, unknown_placeholders, ApplyDescriptionTemplateResponse,
+    DescriptionTemplateDto, MAX_TEMPLATE_LEN, TEMPLATE_ENTITIES,
+};
+use once_cell::sync::Lazy;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Statement,
+    TransactionTrait,
+};
+use tokio::sync::RwLock;
+
+use super::repository;
+use crate::domain::{a012_wb_sales, a015_wb_orders};
+use crate::shared::data::db::get_connection;
+use crate::shared::data::projection_archive;
+use crate::shared::marketplaces::wildberries::datetime::wb_timezone;
+use crate::system::cdc::service::{self as cdc, ChangeEvent, ChangeOp};
+
+/// Документов за один шаг повторного применения (одна транзакция).
+const APPLY_BATCH: u64 = 500;
+
+/// `None` — кэш ещё не загружен.
+static TEMPLATES: Lazy<RwLock<Option<HashMap<String, String>>>> = Lazy::new(|| RwLock::new(None));
+
+async fn reload() -> Result<()> {
+    let map = repository::list_all()
+        .await?
+        .into_iter()
+        .map(|m| (m.entity_type, m.template))
+        .collect();
+    *TEMPLATES.write().await = Some(map);
+    Ok(())
+}
+
+/// Шаблон агрегата; ошибка чтения не мешает импорту — будет описание по умолчанию.
+async fn template_for(entity_type: &str) -> Option<String> {
+    if TEMPLATES.read().await.is_none() {
+        if let Err(e) = reload().await {
+            tracing::warn!("[description_templates] failed to load templates: {e}");
+            return None;
+        }
+    }
+    TEMPLATES
+        .read()
+        .await
+        .as_ref()
+        .and_then(|map| map.get(entity_type).cloned())
+}
+
+fn money(value: Option<f64>) -> String {
+    value.map(|v| format!("{v:.2}")).unwrap_or_default()
+}
+
+fn a012_values(doc: &WbSales) -> Vec<(&'static str, String)> {
+    vec![
+        ("document_no", doc.header.document_no.clone()),
+        ("sale_id", doc.header.sale_id.clone().unwrap_or_default()),
+        ("event_type", doc.state.event_type.clone()),
+        ("supplier_article", doc.line.supplier_article.clone()),
+        ("nm_id", doc.line.nm_id.to_string()),
+        ("product_name", doc.line.name.clone()),
+        (
+            "sale_dt",
+            doc.state
+                .sale_dt
+                .with_timezone(&wb_timezone())
+                .format("%d.%m.%Y")
+                .to_string(),
+        ),
+        ("qty", doc.line.qty.to_string()),
+        ("finished_price", money(doc.line.finished_price)),
+        (
+            "warehouse_name",
+            doc.warehouse.warehouse_name.clone().unwrap_or_default(),
+        ),
+    ]
+}
+
+fn a015_values(doc: &WbOrders) -> Vec<(&'static str, String)> {
+    let order_dt = doc.state.order_dt.with_timezone(&wb_timezone());
+    vec![
+        ("document_no", doc.header.document_no.clone()),
+        ("supplier_article", doc.line.supplier_article.clone()),
+        ("nm_id", doc.line.nm_id.to_string()),
+        ("brand", doc.line.brand.clone().unwrap_or_default()),
+        ("subject", doc.line.subject.clone().unwrap_or_default()),
+        ("order_dt", order_dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+        ("order_date", order_dt.format("%d.%m.%Y").to_string()),
+        ("finished_price", money(doc.line.finished_price)),
+        (
+            "warehouse_name",
+            doc.warehouse.warehouse_name.clone().unwrap_or_default(),
+        ),
+    ]
+}
+
+/// Описание продажи WB по шаблону; `None` — шаблон не задан.
+pub async fn describe_a012(doc: &WbSales) -> Option<String> {
+    let template = template_for("a012_wb_sales").await?;
+    Some(render_template(&template, &a012_values(doc)))
+}
+
+/// Описание заказа WB по шаблону; `None` — шаблон не задан.
+pub async fn describe_a015(doc: &WbOrders) -> Option<String> {
+    let template = template_for("a015_wb_orders").await?;
+    Some(render_template(&template, &a015_values(doc)))
+}
+
+pub async fn list() -> Result<Vec<DescriptionTemplateDto>> {
+    let rows = repository::list_all().await?;
+    Ok(TEMPLATE_ENTITIES
+        .iter()
+        .map(|entity| {
+            let row = rows.iter().find(|r| r.entity_type == entity.entity_type);
+            DescriptionTemplateDto {
+                entity_type: entity.entity_type.to_string(),
+                template: row.map(|r| r.template.clone()),
+                updated_at: row.map(|r| r.updated_at.clone()),
+                updated_by: row.map(|r| r.updated_by.clone()),
+            }
+        })
+        .collect())
+}
+
+pub async fn upsert(
+    entity_type: &str,
+    template: &str,
+    updated_by: &str,
+) -> Result<DescriptionTemplateDto> {
+    let entity = template_entity(entity_type).ok_or_else(|| anyhow!("Entity not found"))?;
+    let template = template.trim();
+    if template.is_empty() || template.chars().count() > MAX_TEMPLATE_LEN {
+        return Err(anyhow!(
+            "Invalid template: required, up to {} characters",
+            MAX_TEMPLATE_LEN
+        ));
+    }
+    let unknown = unknown_placeholders(entity, template);
+    if !unknown.is_empty() {
+        return Err(anyhow!(
+            "Invalid template: unknown placeholders {}",
+            unknown.join(", ")
+        ));
+    }
+
+    let model = repository::Model {
+        entity_type: entity_type.to_string(),
+        template: template.to_string(),
+        updated_at: Utc::now().to_rfc3339(),
+        updated_by: updated_by.to_string(),
+    };
+    repository::upsert(model.clone()).await?;
+    reload().await?;
+    Ok(DescriptionTemplateDto {
+        entity_type: model.entity_type,
+        template: Some(model.template),
+        updated_at: Some(model.updated_at),
+        updated_by: Some(model.updated_by),
+    })
+}
+
+pub async fn delete(entity_type: &str) -> Result<()> {
+    if repository::delete(entity_type).await? == 0 {
+        return Err(anyhow!("Template not found"));
+    }
+    reload().await
+}
+
+/// Записывает изменённые описания одной транзакцией; `table` — из белого списка агрегатов.
+/// Архивированные документы обновляются на месте, в архивной таблице.
+/// События ленты CDC пишутся в той же транзакции.
+async fn save_descriptions(table: &'static str, changes: &[(String, String)]) -> Result<()> {
+    if changes.is_empty() {
+        return Ok(());
+    }
+    let txn = get_connection().begin().await?;
+    for document_table in projection_archive::physical_tables(table) {
+        let sql = format!("UPDATE {document_table} SET description = ? WHERE id = ?");
+        for (id, description) in changes {
+            txn.execute(Statement::from_sql_and_values(
+                txn.get_database_backend(),
+                &sql,
+                [description.clone().into(), id.clone().into()],
+            ))
+            .await?;
+        }
+    }
+    let events: Vec<ChangeEvent> = changes
+        .iter()
+        .map(|(id, _)| ChangeEvent::aggregate(table, id.clone(), ChangeOp::Updated))
+        .collect();
+    cdc::record_with_conn(&txn, &events).await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+async fn apply_a012(template: &str) -> Result<ApplyDescriptionTemplateResponse> {
+    use a012_wb_sales::repository::{Column, Entity};
+    let mut result = ApplyDescriptionTemplateResponse {
+        scanned: 0,
+        updated: 0,
+    };
+    loop {
+        let models = projection_archive::route_select(
+            "a012_wb_sales",
+            None,
+            Entity::find()
+                .filter(Column::IsDeleted.eq(false))
+                .order_by_asc(Column::Id)
+                .offset(result.scanned)
+                .limit(APPLY_BATCH),
+        )
+        .all(get_connection())
+        .await?;
+        if models.is_empty() {
+            break;
+        }
+        result.scanned += models.len() as u64;
+        let changes: Vec<(String, String)> = models
+            .into_iter()
+            .filter_map(|model| {
+                let id = model.id.clone();
+                let current = model.description.clone();
+                let doc: WbSales = model.into();
+                let description = render_template(template, &a012_values(&doc));
+                (description != current).then_some((id, description))
+            })
+            .collect();
+        result.updated += changes.len() as u64;
+        save_descriptions("a012_wb_sales", &changes).await?;
+    }
+    Ok(result)
+}
+
+async fn apply_a015(template: &str) -> Result<ApplyDescriptionTemplateResponse> {
+    use a015_wb_orders::repository::{Column, Entity};
+    let mut result = ApplyDescriptionTemplateResponse {
+        scanned: 0,
+        updated: 0,
+    };
+    loop {
+        let models = Entity::find()
+            .filter(Column::IsDeleted.eq(false))
+            .order_by_asc(Column::Id)
+            .offset(result.scanned)
+            .limit(APPLY_BATCH)
+            .all(get_connection())
+            .await?;
+        if models.is_empty() {
+            break;
+        }
+        result.scanned += models.len() as u64;
+        let changes: Vec<(String, String)> = models
+            .into_iter()
+            .filter_map(|model| {
+                let id = model.id.clone();
+                let current = model.description.clone();
+                let doc: WbOrders = model.into();
+                let description = render_template(template, &a015_values(&doc));
+                (description != current).then_some((id, description))
+            })
+            .collect();
+        result.updated += changes.len() as u64;
+        save_descriptions("a015_wb_orders", &changes).await?;
+    }
+    Ok(result)
+}
+
+/// Пересобирает описания уже загруженных документов по текущему шаблону.
+pub async fn apply(entity_type: &str) -> Result<ApplyDescriptionTemplateResponse> {
+    template_entity(entity_type).ok_or_else(|| anyhow!("Entity not found"))?;
+    let template = template_for(entity_type)
+        .await
+        .ok_or_else(|| anyhow!("Invalid request: template is not set"))?;
+    match entity_type {
+        "a012_wb_sales" => apply_a012(&template).await,
+        "a015_wb_orders" => apply_a015(&template).await,
+        _ => Err(anyhow!("Entity not found")),
+    }
+}
@@ -0,0 +1,501 @@
+//! Журнал действий с документами (`sys_audit_log`) для вкладки «История» карточки.
+//!
+//! Проведение и отмена проведения пишутся самой командой через [`record_with_conn`]
+//! на её соединении (транзакция `UnitOfWork`/`txn`), поэтому запись журнала
+//! фиксируется и откатывается вместе с документом. Пользователь берётся из контекста
+//! задачи: его задают [`track`] для HTTP-запросов (в том числе `post-period`) и
+//! фоновые операции (`batch-post`, `batch-unpost`) через [`as_user`]. Регламентные
+//! задачи и импорт выполняются без пользователя и в журнал не попадают.
+//!
+//! Остальные изменения записывает [`track`] — обёртка вокруг обработчика, которую
+//! проверка доступа `check_scope` вызывает для записывающих запросов к агрегатам:
+//! - `PUT`/`PATCH …/:id…` — изменение, `DELETE …/:id` — удаление;
+//! - `POST` с JSON-объектом без id в пути — upsert: id берётся из тела или из ответа,
+//!   «создан», если строки документа до запроса не было, иначе «изменён».
+//!
+//! Строка документа читается до и после обработчика; разница колонок — сводка
+//! изменений. Пишем только при успешном ответе и только если документ нашёлся в
+//! таблице агрегата; ошибка такой записи журнала не ломает сам запрос.
+
+pub mod repository;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Method, Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use contracts::system::auth::TokenClaims;
+use contracts::system::document_audit::DocumentAuditAction;
+use sea_orm::ConnectionTrait;
+use serde_json::{Map, Value};
+use std::future::Future;
+use uuid::Uuid;
+
+tokio::task_local! {
+    /// Пользователь, от имени которого выполняется запрос или фоновая операция.
+    static ACTOR: String;
+}
+
+/// Выполняет `fut` от имени `user_id`: проведения внутри попадают в журнал.
+/// Задачи, запущенные внутри через `tokio::spawn`, пользователя не наследуют.
+pub async fn as_user<F: Future>(user_id: &str, fut: F) -> F::Output {
+    ACTOR.scope(user_id.to_string(), fut).await
+}
+
+/// Пишет проведение или отмену проведения в журнал на соединении команды.
+/// Ошибка записи откатывает команду; без пользователя в контексте ничего не пишет.
+pub async fn record_with_conn<C: ConnectionTrait>(
+    conn: &C,
+    entity_type: &str,
+    entity_id: &str,
+    action: DocumentAuditAction,
+) -> anyhow::Result<()> {
+    let Ok(user_id) = ACTOR.try_with(String::clone) else {
+        return Ok(());
+    };
+    repository::insert_with_conn(conn, entity_type, entity_id, &user_id, action, None).await?;
+    Ok(())
+}
+
+/// [`record_with_conn`] для команд, которые пишут документ без транзакции:
+/// вызывать сразу после записи документа.
+pub async fn record(
+    entity_type: &str,
+    entity_id: &str,
+    action: DocumentAuditAction,
+) -> anyhow::Result<()> {
+    record_with_conn(
+        crate::shared::data::db::get_connection(),
+        entity_type,
+        entity_id,
+        action,
+    )
+    .await
+}
+
+/// Колонки, которые меняются при любой записи и только засоряют сводку.
+const IGNORED_COLUMNS: &[&str] = &["created_at", "updated_at", "version"];
+
+/// Сколько изменённых полей перечисляем в сводке, остальные — «и ещё N».
+const MAX_SUMMARY_FIELDS: usize = 8;
+
+/// Длинные значения (JSON, описания) обрезаются в сводке.
+const MAX_VALUE_CHARS: usize = 60;
+
+/// Таблица агрегата по его scope (`a012_wb_sales`); `None` — scope не агрегат.
+pub fn table_for_scope(scope_id: &str) -> Option<&str> {
+    let bytes = scope_id.as_bytes();
+    let is_aggregate = bytes.len() > 5
+        && bytes[0] == b'a'
+        && bytes[1..4].iter().all(u8::is_ascii_digit)
+        && bytes[4] == b'_';
+    if !is_aggregate {
+        return None;
+    }
+    Some(match scope_id {
+        "a001_connection_1c" => "a001_connection_1c_database",
+        other => other,
+    })
+}
+
+/// Организация документа по его строке: колонка `organization_id` /
+/// `organization_ref` или `organization_id` в `header_json`. `None` — у агрегата нет
+/// организации (справочники, закрытие дня): такая история не фильтруется.
+pub fn row_organization(row: &Map<String, Value>) -> Option<String> {
+    ["organization_id", "organization_ref"]
+        .iter()
+        .find_map(|column| row.get(*column).and_then(Value::as_str))
+        .map(str::to_string)
+        .or_else(|| {
+            let header = row.get("header_json").and_then(Value::as_str)?;
+            serde_json::from_str::<Value>(header)
+                .ok()?
+                .get("organization_id")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+}
+
+fn is_document_id(value: &str) -> bool {
+    Uuid::parse_str(value).is_ok_and(|id| !id.is_nil())
+}
+
+#[derive(Debug, PartialEq)]
+enum Target {
+    /// Документ и действие определяются путём
+    Document {
+        id: String,
+        action: DocumentAuditAction,
+    },
+    /// POST-upsert: id в теле запроса или в ответе
+    Upsert,
+}
+
+fn classify(method: &Method, path: &str) -> Option<Target> {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let last = *segments.last()?;
+    let document = |id: &str, action| {
+        Some(Target::Document {
+            id: id.to_string(),
+            action,
+        })
+    };
+
+    match *method {
+        Method::POST => {
+            if segments.len() >= 2 && is_document_id(segments[segments.len() - 2]) {
+                let id = segments[segments.len() - 2];
+                return match last {
+                    "post" => document(id, DocumentAuditAction::Posted),
+                    "unpost" => document(id, DocumentAuditAction::Unposted),
+                    _ => None,
+                };
+            }
+            if segments.iter().any(|s| is_document_id(s)) {
+                None
+            } else {
+                Some(Target::Upsert)
+            }
+        }
+        Method::PUT | Method::PATCH => segments
+            .iter()
+            .rev()
+            .find(|s| is_document_id(s))
+            .and_then(|id| document(id, DocumentAuditAction::Updated)),
+        Method::DELETE if is_document_id(last) => document(last, DocumentAuditAction::Deleted),
+        _ => None,
+    }
+}
+
+fn short_value(value: &Value) -> String {
+    let text = match value {
+        Value::Null => "—".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if text.chars().count() > MAX_VALUE_CHARS {
+        format!(
+            "{}…",
+            text.chars().take(MAX_VALUE_CHARS).collect::<String>()
+        )
+    } else {
+        text
+    }
+}
+
+/// Сводка «поле: было → стало» по колонкам строки документа; `None` — изменений нет
+/// или одной из версий строки нет (создание, физическое удаление).
+pub fn diff_summary(
+    before: Option<&Map<String, Value>>,
+    after: Option<&Map<String, Value>>,
+) -> Option<String> {
+    let (before, after) = (before?, after?);
+    let mut changes: Vec<String> = after
+        .iter()
+        .filter(|(column, _)| !IGNORED_COLUMNS.contains(&column.as_str()))
+        .filter_map(|(column, new)| {
+            let old = before.get(column).unwrap_or(&Value::Null);
+            (old != new).then(|| format!("{}: {} → {}", column, short_value(old), short_value(new)))
+        })
+        .collect();
+    if changes.is_empty() {
+        return None;
+    }
+    let hidden = changes.len().saturating_sub(MAX_SUMMARY_FIELDS);
+    changes.truncate(MAX_SUMMARY_FIELDS);
+    let mut summary = changes.join("; ");
+    if hidden > 0 {
+        summary.push_str(&format!("; и ещё {}", hidden));
+    }
+    Some(summary)
+}
+
+async fn write(
+    scope_id: &str,
+    id: &str,
+    claims: &TokenClaims,
+    action: DocumentAuditAction,
+    summary: Option<String>,
+) {
+    if let Err(e) = repository::insert(scope_id, id, &claims.sub, action, summary.as_deref()).await
+    {
+        tracing::warn!(
+            "document audit: failed to record {} {} {}: {}",
+            action.code(),
+            scope_id,
+            id,
+            e
+        );
+    }
+}
+
+/// Выполняет обработчик от имени пользователя и пишет запись журнала, если запрос
+/// изменил документ агрегата.
+pub async fn track(
+    scope_id: &'static str,
+    claims: &TokenClaims,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    as_user(&claims.sub, track_changes(scope_id, claims, req, next)).await
+}
+
+async fn track_changes(
+    scope_id: &'static str,
+    claims: &TokenClaims,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(table) = table_for_scope(scope_id) else {
+        return next.run(req).await;
+    };
+    match classify(req.method(), req.uri().path()) {
+        // Проведение пишет журнал в своей транзакции
+        Some(Target::Document {
+            action: DocumentAuditAction::Posted | DocumentAuditAction::Unposted,
+            ..
+        }) => next.run(req).await,
+        Some(Target::Document { id, action }) => {
+            let before = repository::snapshot(table, &id).await;
+            let response = next.run(req).await;
+            if response.status().is_success() {
+                let after = repository::snapshot(table, &id).await;
+                if before.is_some() || after.is_some() {
+                    let summary = diff_summary(before.as_ref(), after.as_ref());
+                    write(scope_id, &id, claims, action, summary).await;
+                }
+            }
+            response
+        }
+        Some(Target::Upsert) => track_upsert(scope_id, table, claims, req, next).await,
+        None => next.run(req).await,
+    }
+}
+
+async fn track_upsert(
+    scope_id: &'static str,
+    table: &str,
+    claims: &TokenClaims,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let (parts, body) = req.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let body_id = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(Value::Object(object)) => object
+            .get("id")
+            .and_then(Value::as_str)
+            .filter(|id| is_document_id(id))
+            .map(str::to_string),
+        _ => {
+            return next
+                .run(Request::from_parts(parts, Body::from(bytes)))
+                .await
+        }
+    };
+
+    let before = match &body_id {
+        Some(id) => repository::snapshot(table, id).await,
+        None => None,
+    };
+    let response = next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await;
+    if !response.status().is_success() {
+        return response;
+    }
+    let (response, id) = match body_id {
+        Some(id) => (response, Some(id)),
+        None => response_id(response).await,
+    };
+    let Some(id) = id else {
+        return response;
+    };
+
+    // Строки нет — это был не upsert документа, а другое действие с JSON-телом
+    let Some(after) = repository::snapshot(table, &id).await else {
+        return response;
+    };
+    let (action, summary) = match before {
+        Some(before) => (
+            DocumentAuditAction::Updated,
+            diff_summary(Some(&before), Some(&after)),
+        ),
+        None => (DocumentAuditAction::Created, None),
+    };
+    write(scope_id, &id, claims, action, summary).await;
+    response
+}
+
+/// id созданного документа из ответа: `{"id": "…"}` или просто строка.
+async fn response_id(response: Response) -> (Response, Option<String>) {
+    let (parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return (Response::from_parts(parts, Body::empty()), None);
+    };
+    let id = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(Value::String(id)) => Some(id),
+        Ok(value) => value.get("id").and_then(Value::as_str).map(str::to_string),
+        Err(_) => None,
+    }
+    .filter(|id| is_document_id(id));
+    (Response::from_parts(parts, Body::from(bytes)), id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ID: &str = "5f0c3a9e-7c1d-4b6e-9a55-2f1f4c3f8d21";
+
+    fn row(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn classifies_document_write_routes() {
+        let posted = classify(&Method::POST, &format!("/api/a012/wb-sales/{ID}/post"));
+        assert_eq!(
+            posted,
+            Some(Target::Document {
+                id: ID.to_string(),
+                action: DocumentAuditAction::Posted
+            })
+        );
+        assert_eq!(
+            classify(&Method::DELETE, &format!("/api/organization/{ID}")),
+            Some(Target::Document {
+                id: ID.to_string(),
+                action: DocumentAuditAction::Deleted
+            })
+        );
+        assert_eq!(
+            classify(&Method::POST, "/api/organization"),
+            Some(Target::Upsert)
+        );
+        assert_eq!(
+            classify(
+                &Method::POST,
+                &format!("/api/a012/wb-sales/{ID}/finance-links")
+            ),
+            None
+        );
+        assert_eq!(
+            classify(&Method::GET, &format!("/api/organization/{ID}")),
+            None
+        );
+    }
+
+    #[test]
+    fn only_aggregate_scopes_are_audited() {
+        assert_eq!(table_for_scope("a012_wb_sales"), Some("a012_wb_sales"));
+        assert_eq!(
+            table_for_scope("a001_connection_1c"),
+            Some("a001_connection_1c_database")
+        );
+        assert_eq!(table_for_scope("p903_wb_finance_report"), None);
+        assert_eq!(table_for_scope("knowledge_base"), None);
+    }
+
+    #[test]
+    fn organization_is_read_from_column_or_header() {
+        assert_eq!(
+            row_organization(&row(json!({"id": ID, "organization_id": "org-a"}))).as_deref(),
+            Some("org-a")
+        );
+        assert_eq!(
+            row_organization(&row(
+                json!({"id": ID, "header_json": "{\"organization_id\":\"org-b\"}"})
+            ))
+            .as_deref(),
+            Some("org-b")
+        );
+        assert_eq!(
+            row_organization(&row(json!({"id": ID, "article": "x"}))),
+            None
+        );
+    }
+
+    #[test]
+    fn summary_lists_changed_columns_only() {
+        let before = row(
+            json!({"id": ID, "is_posted": 0, "comment": null, "updated_at": "a", "version": 1}),
+        );
+        let after = row(
+            json!({"id": ID, "is_posted": 1, "comment": "проверено", "updated_at": "b", "version": 2}),
+        );
+        assert_eq!(
+            diff_summary(Some(&before), Some(&after)).as_deref(),
+            Some("comment: — → проверено; is_posted: 0 → 1")
+        );
+        assert_eq!(diff_summary(Some(&before), Some(&before)), None);
+        assert_eq!(diff_summary(None, Some(&after)), None);
+    }
+
+    async fn audit_rows(db: &sea_orm::DatabaseConnection) -> Vec<(String, String)> {
+        db.query_all(sea_orm::Statement::from_string(
+            sea_orm::DatabaseBackend::Sqlite,
+            "SELECT user_id, action FROM sys_audit_log ORDER BY id",
+        ))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| {
+            (
+                row.try_get::<String>("", "user_id").unwrap(),
+                row.try_get::<String>("", "action").unwrap(),
+            )
+        })
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn posting_entry_follows_the_command_transaction() {
+        use sea_orm::{ConnectOptions, Database, TransactionTrait};
+
+        let mut options = ConnectOptions::new("sqlite::memory:");
+        options.max_connections(1);
+        let db = Database::connect(options).await.unwrap();
+        db.execute_unprepared(
+            "CREATE TABLE sys_audit_log (id INTEGER PRIMARY KEY AUTOINCREMENT, user_id TEXT, \
+             action TEXT NOT NULL, entity_type TEXT, entity_id TEXT, details TEXT, \
+             created_at TEXT NOT NULL)",
+        )
+        .await
+        .unwrap();
+
+        // Без пользователя (регламентная задача) — не пишем
+        let txn = db.begin().await.unwrap();
+        record_with_conn(&txn, "a012_wb_sales", ID, DocumentAuditAction::Posted)
+            .await
+            .unwrap();
+        txn.commit().await.unwrap();
+        assert!(audit_rows(&db).await.is_empty());
+
+        as_user("u1", async {
+            // Откат проведения убирает и запись журнала
+            let txn = db.begin().await.unwrap();
+            record_with_conn(&txn, "a012_wb_sales", ID, DocumentAuditAction::Posted)
+                .await
+                .unwrap();
+            txn.rollback().await.unwrap();
+
+            let txn = db.begin().await.unwrap();
+            record_with_conn(&txn, "a012_wb_sales", ID, DocumentAuditAction::Unposted)
+                .await
+                .unwrap();
+            txn.commit().await.unwrap();
+        })
+        .await;
+        assert_eq!(
+            audit_rows(&db).await,
+            vec![("u1".to_string(), "unposted".to_string())]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use chrono::Utc;
+use contracts::system::document_audit::{DocumentAuditAction, DocumentAuditEntryDto};
+use sea_orm::{ConnectionTrait, DbErr, FromQueryResult, Statement};
+use serde_json::{Map, Value};
+
+use crate::shared::data::db::get_connection;
+use crate::shared::data_access::row_json::{fetch_json_rows, JsonBind};
+
+/// Сколько последних записей отдаём на вкладку «История».
+pub const LIST_LIMIT: i64 = 500;
+
+#[derive(Debug, FromQueryResult)]
+struct EntryRow {
+    id: i64,
+    created_at: String,
+    user_id: Option<String>,
+    username: Option<String>,
+    action: String,
+    details: Option<String>,
+}
+
+pub async fn insert(
+    entity_type: &str,
+    entity_id: &str,
+    user_id: &str,
+    action: DocumentAuditAction,
+    summary: Option<&str>,
+) -> Result<(), DbErr> {
+    insert_with_conn(
+        get_connection(),
+        entity_type,
+        entity_id,
+        user_id,
+        action,
+        summary,
+    )
+    .await
+}
+
+/// Запись журнала на соединении команды — в транзакции проведения.
+pub async fn insert_with_conn<C: ConnectionTrait>(
+    db: &C,
+    entity_type: &str,
+    entity_id: &str,
+    user_id: &str,
+    action: DocumentAuditAction,
+    summary: Option<&str>,
+) -> Result<(), DbErr> {
+    db.execute(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        "INSERT INTO sys_audit_log (user_id, action, entity_type, entity_id, details, created_at) \
+         VALUES (?, ?, ?, ?, ?, ?)",
+        vec![
+            user_id.into(),
+            action.code().into(),
+            entity_type.into(),
+            entity_id.into(),
+            summary.map(str::to_string).into(),
+            Utc::now().to_rfc3339().into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Записи по документу, новые сверху. Строки `sys_audit_log` с чужими действиями
+/// (не из [`DocumentAuditAction`]) пропускаются.
+pub async fn list(entity_type: &str, entity_id: &str) -> Result<Vec<DocumentAuditEntryDto>, DbErr> {
+    let db = get_connection();
+    let rows = EntryRow::find_by_statement(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        "SELECT l.id, l.created_at, l.user_id, u.username, l.action, l.details \
+         FROM sys_audit_log l \
+         LEFT JOIN sys_users u ON u.id = l.user_id \
+         WHERE l.entity_type = ? AND l.entity_id = ? \
+         ORDER BY l.id DESC \
+         LIMIT ?",
+        vec![entity_type.into(), entity_id.into(), LIST_LIMIT.into()],
+    ))
+    .all(db)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            Some(DocumentAuditEntryDto {
+                action: DocumentAuditAction::from_code(&row.action)?,
+                id: row.id,
+                created_at: row.created_at,
+                user_id: row.user_id,
+                username: row.username,
+                summary: row.details,
+            })
+        })
+        .collect())
+}
+
+/// Строка документа как JSON-объект колонок; `None` — строки нет или чтение не удалось.
+/// `table` — только из [`super::table_for_scope`] (или его источник с архивом из
+/// `projection_archive::source`), не из запроса.
+pub async fn snapshot(table: &str, id: &str) -> Option<Map<String, Value>> {
+    let sql = format!("SELECT * FROM {table} WHERE id = ?");
+    match fetch_json_rows(&sql, vec![JsonBind::Text(id.to_string())]).await {
+        Ok((rows, _)) => rows.into_iter().next().and_then(|row| match row {
+            Value::Object(map) => Some(map),
+            _ => None,
+        }),
+        Err(e) => {
+            tracing::warn!("document audit: failed to read {} {}: {}", table, id, e);
+            None
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Режим окружения и подтверждение опасных операций. В staging/prod опасный
+//! эндпоинт принимает запрос только с токеном, выданным тому же пользователю
+//! на ту же операцию (метод + путь); токен живёт несколько минут, чтобы
+//! пакетная очистка по документам не спрашивала подтверждение на каждый.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use contracts::system::auth::TokenClaims;
+use contracts::system::environment::{
+    danger_action, DangerConfirmRequest, DangerConfirmResponse, EnvironmentInfoDto,
+    EnvironmentMode, CONFIRM_TOKEN_HEADER, CONFIRM_TOKEN_TTL_SECS,
+};
+use once_cell::sync::Lazy;
+
+use crate::shared::config::get_environment_config;
+
+struct IssuedToken {
+    user_id: String,
+    action: String,
+    created: Instant,
+}
+
+static TOKENS: Lazy<Mutex<HashMap<String, IssuedToken>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn ttl() -> Duration {
+    Duration::from_secs(CONFIRM_TOKEN_TTL_SECS as u64)
+}
+
+pub fn mode() -> EnvironmentMode {
+    get_environment_config().mode
+}
+
+pub fn info() -> EnvironmentInfoDto {
+    let cfg = get_environment_config();
+    EnvironmentInfoDto {
+        mode: cfg.mode,
+        name: cfg.name.clone(),
+    }
+}
+
+/// Выдаёт токен подтверждения; в dev токен не нужен.
+pub fn issue_token(user_id: &str, req: &DangerConfirmRequest) -> Result<DangerConfirmResponse> {
+    let mode = mode();
+    if !mode.requires_confirmation() {
+        return Ok(DangerConfirmResponse {
+            token: None,
+            expires_at: None,
+        });
+    }
+    if req.action.trim().is_empty() {
+        return Err(anyhow!("Invalid request: action is required"));
+    }
+    if mode.requires_typed_confirmation()
+        && req.typed_mode.as_deref().map(str::trim) != Some(mode.code())
+    {
+        return Err(anyhow!(
+            "Invalid confirmation: type \"{}\" to confirm",
+            mode.code()
+        ));
+    }
+
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    let mut tokens = TOKENS.lock().unwrap();
+    tokens.retain(|_, t| t.created.elapsed() < ttl());
+    tokens.insert(
+        token.clone(),
+        IssuedToken {
+            user_id: user_id.to_string(),
+            action: req.action.trim().to_string(),
+            created: Instant::now(),
+        },
+    );
+    tracing::warn!(
+        "[environment] {} confirmation issued for {} by user {}",
+        mode.code(),
+        req.action.trim(),
+        user_id
+    );
+    Ok(DangerConfirmResponse {
+        token: Some(token),
+        expires_at: Some(
+            (chrono::Utc::now() + chrono::Duration::seconds(CONFIRM_TOKEN_TTL_SECS)).to_rfc3339(),
+        ),
+    })
+}
+
+fn token_matches(token: &str, user_id: &str, action: &str) -> bool {
+    TOKENS
+        .lock()
+        .unwrap()
+        .get(token)
+        .is_some_and(|t| t.user_id == user_id && t.action == action && t.created.elapsed() < ttl())
+}
+
+/// Слой для опасных эндпоинтов: в staging/prod без действующего токена — 428.
+/// Ставится внутри слоя авторизации — нужен `TokenClaims` в extensions.
+pub async fn require_confirmation(req: Request<Body>, next: Next) -> Response {
+    let mode = mode();
+    if !mode.requires_confirmation() {
+        return next.run(req).await;
+    }
+    let action = danger_action(req.method().as_str(), req.uri().path());
+    let Some(user_id) = req.extensions().get::<TokenClaims>().map(|c| c.sub.clone()) else {
+        return StatusCode::UNAUTHORIZED.into_response();
+    };
+    let token = req
+        .headers()
+        .get(CONFIRM_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if !token_matches(token, &user_id, &action) {
+        tracing::warn!(
+            "[environment] {} blocked in {} mode: no confirmation",
+            action,
+            mode.code()
+        );
+        return (
+            StatusCode::PRECONDITION_REQUIRED,
+            format!("Операция в режиме {} требует подтверждения", mode.code()),
+        )
+            .into_response();
+    }
+    next.run(req).await
+}
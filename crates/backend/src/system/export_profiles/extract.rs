@@ -0,0 +1,312 @@
+//! Чтение документов по профилю: SQL-выражения колонок каталога
+//! ([`contracts::system::export_profiles::EXPORT_PROFILE_ENTITIES`]) и отбор.
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{Duration, NaiveDate};
+use contracts::system::export_profiles::{entity_info, ExportProfileFilter};
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement, Value};
+
+use crate::shared::data::db::get_connection;
+
+/// Максимум строк в одном файле профиля.
+pub const MAX_ROWS: usize = 200_000;
+
+/// Колонки с суммами: без доступа к финансам (`view_finance`) выгружаются пустыми.
+pub const FINANCE_COLUMNS: &[&str] = &["total_price", "finished_price"];
+
+/// Тип значения колонки — от него зависит ячейка XLSX и значение NDJSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    Text,
+    Number,
+    Bool,
+}
+
+/// Значение ячейки, прочитанное из БД.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    Null,
+}
+
+/// Дата документа в таблице агрегата (для отбора по периоду).
+fn date_expr(entity_type: &str) -> Option<&'static str> {
+    match entity_type {
+        "a012_wb_sales" => Some("d.sale_date"),
+        "a015_wb_orders" => Some("d.document_date"),
+        "a010_ozon_fbs_posting" => Some("json_extract(d.state_json, '$.delivered_at')"),
+        "a011_ozon_fbo_posting" => Some("d.created_at_source"),
+        _ => None,
+    }
+}
+
+fn connection_expr(entity_type: &str) -> &'static str {
+    match entity_type {
+        "a012_wb_sales" => "d.connection_id",
+        _ => "json_extract(d.header_json, '$.connection_id')",
+    }
+}
+
+fn organization_expr(entity_type: &str) -> &'static str {
+    match entity_type {
+        "a012_wb_sales" => "d.organization_id",
+        _ => "json_extract(d.header_json, '$.organization_id')",
+    }
+}
+
+/// SQL-выражение и тип колонки; `None` — колонки нет у этого типа документов.
+/// `entity_type` подставляется в SQL как есть — только значения из каталога.
+pub fn column_sql(entity_type: &str, key: &str) -> Option<(String, ColumnKind)> {
+    let column = |name: &str, kind| Some((format!("d.{name}"), kind));
+    match key {
+        "document_no" => column("document_no", ColumnKind::Text),
+        "document_date" => date_expr(entity_type).map(|e| (e.to_string(), ColumnKind::Text)),
+        "is_posted" => column("is_posted", ColumnKind::Bool),
+        "created_at" => column("created_at", ColumnKind::Text),
+        "tags" => Some((
+            format!(
+                "(SELECT group_concat(t.tag, ', ') FROM sys_document_tags t \
+                 WHERE t.entity_type = '{entity_type}' AND t.entity_id = d.id)"
+            ),
+            ColumnKind::Text,
+        )),
+        _ => match (entity_type, key) {
+            ("a012_wb_sales", "event_type" | "supplier_article" | "barcode" | "product_name") => {
+                column(key, ColumnKind::Text)
+            }
+            ("a012_wb_sales", "nm_id" | "qty" | "total_price" | "finished_price") => {
+                column(key, ColumnKind::Number)
+            }
+            ("a015_wb_orders", "g_number" | "cancel_date") => column(key, ColumnKind::Text),
+            ("a015_wb_orders", "spp") => column(key, ColumnKind::Number),
+            ("a015_wb_orders", "is_cancel") => column(key, ColumnKind::Bool),
+            (
+                "a010_ozon_fbs_posting" | "a011_ozon_fbo_posting",
+                "status_norm" | "substatus_raw",
+            ) => column(key, ColumnKind::Text),
+            _ => None,
+        },
+    }
+}
+
+/// Запрос выборки и его параметры (отбор). `today` — последний день периода «последние N дней».
+/// `organizations` — организации владельца профиля (`None` — все, для администратора).
+pub fn build_query(
+    entity_type: &str,
+    filter: &ExportProfileFilter,
+    columns: &[String],
+    organizations: Option<&[String]>,
+    today: NaiveDate,
+) -> Result<(String, Vec<ColumnKind>, Vec<Value>)> {
+    let Some(entity) = entity_info(entity_type) else {
+        bail!("Неизвестный тип документов: {}", entity_type);
+    };
+    let entity_type = entity.entity_type;
+    let mut select = Vec::with_capacity(columns.len());
+    let mut kinds = Vec::with_capacity(columns.len());
+    for (i, key) in columns.iter().enumerate() {
+        let (expr, kind) =
+            column_sql(entity_type, key).ok_or_else(|| anyhow!("Неизвестная колонка: {}", key))?;
+        select.push(format!("{expr} AS c{i}"));
+        kinds.push(kind);
+    }
+
+    let mut values: Vec<Value> = Vec::new();
+    let mut conditions = vec!["d.is_deleted = 0".to_string()];
+    if filter.posted_only {
+        conditions.push("d.is_posted = 1".to_string());
+    }
+    if let Some(connection_id) = filter.connection_id.as_deref().filter(|c| !c.is_empty()) {
+        values.push(connection_id.into());
+        conditions.push(format!(
+            "{} = ?{}",
+            connection_expr(entity_type),
+            values.len()
+        ));
+    }
+    if let Some(organizations) = organizations {
+        let mut placeholders = Vec::with_capacity(organizations.len());
+        for organization_id in organizations {
+            values.push(
+                organization_id
+                    .replace('"', "")
+                    .trim()
+                    .to_lowercase()
+                    .into(),
+            );
+            placeholders.push(format!("?{}", values.len()));
+        }
+        if placeholders.is_empty() {
+            conditions.push("0 = 1".to_string());
+        } else {
+            conditions.push(format!(
+                "LOWER(TRIM(REPLACE(COALESCE({}, ''), '\"', ''))) IN ({})",
+                organization_expr(entity_type),
+                placeholders.join(", ")
+            ));
+        }
+    }
+    if let Some(days) = filter.period_days {
+        let date = date_expr(entity_type).unwrap_or("d.created_at");
+        let from = today - Duration::days(days.max(1) - 1);
+        values.push(from.format("%Y-%m-%d").to_string().into());
+        conditions.push(format!("substr({date}, 1, 10) >= ?{}", values.len()));
+    }
+    if !filter.tags.is_empty() {
+        let mut placeholders = Vec::with_capacity(filter.tags.len());
+        for tag in &filter.tags {
+            values.push(tag.trim().to_string().into());
+            placeholders.push(format!("?{}", values.len()));
+        }
+        conditions.push(format!(
+            "EXISTS (SELECT 1 FROM sys_document_tags t \
+             WHERE t.entity_type = '{}' AND t.entity_id = d.id AND t.tag IN ({}))",
+            entity_type,
+            placeholders.join(", ")
+        ));
+    }
+
+    let order = date_expr(entity_type).unwrap_or("d.created_at");
+    let sql = format!(
+        "SELECT {} FROM {} d WHERE {} ORDER BY {}, d.document_no LIMIT {}",
+        select.join(", "),
+        entity_type,
+        conditions.join(" AND "),
+        order,
+        MAX_ROWS + 1
+    );
+    Ok((sql, kinds, values))
+}
+
+/// Строки документов по профилю; больше [`MAX_ROWS`] — ошибка (сузьте отбор).
+pub async fn fetch_rows(
+    entity_type: &str,
+    filter: &ExportProfileFilter,
+    columns: &[String],
+    organizations: Option<&[String]>,
+    today: NaiveDate,
+) -> Result<Vec<Vec<CellValue>>> {
+    let (sql, kinds, values) = build_query(entity_type, filter, columns, organizations, today)?;
+    let rows = get_connection()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            &sql,
+            values,
+        ))
+        .await?;
+    if rows.len() > MAX_ROWS {
+        bail!(
+            "Больше {} строк — сузьте отбор профиля (период, метки, кабинет)",
+            MAX_ROWS
+        );
+    }
+    rows.iter()
+        .map(|row| {
+            kinds
+                .iter()
+                .enumerate()
+                .map(|(i, kind)| {
+                    let col = format!("c{i}");
+                    Ok(match kind {
+                        ColumnKind::Text => row
+                            .try_get::<Option<String>>("", &col)?
+                            .map(CellValue::Text)
+                            .unwrap_or(CellValue::Null),
+                        ColumnKind::Number => row
+                            .try_get::<Option<f64>>("", &col)?
+                            .map(CellValue::Number)
+                            .unwrap_or(CellValue::Null),
+                        ColumnKind::Bool => row
+                            .try_get::<Option<i64>>("", &col)?
+                            .map(|v| CellValue::Bool(v != 0))
+                            .unwrap_or(CellValue::Null),
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::system::export_profiles::EXPORT_PROFILE_ENTITIES;
+
+    #[test]
+    fn every_catalog_column_has_sql() {
+        for entity in EXPORT_PROFILE_ENTITIES {
+            for column in entity.columns {
+                assert!(
+                    column_sql(entity.entity_type, column.key).is_some(),
+                    "{}.{}",
+                    entity.entity_type,
+                    column.key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn query_numbers_filter_parameters_in_order() {
+        let filter = ExportProfileFilter {
+            tags: vec!["партнёр-x".to_string(), "vip".to_string()],
+            connection_id: Some("conn-1".to_string()),
+            period_days: Some(7),
+            posted_only: true,
+        };
+        let (sql, kinds, values) = build_query(
+            "a015_wb_orders",
+            &filter,
+            &["document_no".to_string(), "spp".to_string()],
+            None,
+            NaiveDate::from_ymd_opt(2026, 10, 17).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(kinds, vec![ColumnKind::Text, ColumnKind::Number]);
+        assert_eq!(values.len(), 4);
+        assert_eq!(values[1], Value::from("2026-10-11".to_string()));
+        assert!(sql.contains("json_extract(d.header_json, '$.connection_id') = ?1"));
+        assert!(sql.contains("substr(d.document_date, 1, 10) >= ?2"));
+        assert!(sql.contains("t.tag IN (?3, ?4)"));
+        assert!(sql.contains("d.is_posted = 1"));
+    }
+
+    #[test]
+    fn query_rejects_column_of_another_entity() {
+        assert!(build_query(
+            "a010_ozon_fbs_posting",
+            &ExportProfileFilter::default(),
+            &["spp".to_string()],
+            None,
+            NaiveDate::from_ymd_opt(2026, 10, 17).unwrap(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn query_keeps_rows_of_owner_organizations_only() {
+        let organizations = vec!["\"ORG-A\"".to_string(), "org-b".to_string()];
+        let (sql, _, values) = build_query(
+            "a012_wb_sales",
+            &ExportProfileFilter::default(),
+            &["document_no".to_string()],
+            Some(&organizations),
+            NaiveDate::from_ymd_opt(2026, 10, 17).unwrap(),
+        )
+        .unwrap();
+        assert!(sql.contains("COALESCE(d.organization_id, ''), '\"', ''))) IN (?1, ?2)"));
+        assert_eq!(values[0], Value::from("org-a".to_string()));
+
+        let (sql, _, _) = build_query(
+            "a015_wb_orders",
+            &ExportProfileFilter::default(),
+            &["document_no".to_string()],
+            Some(&[]),
+            NaiveDate::from_ymd_opt(2026, 10, 17).unwrap(),
+        )
+        .unwrap();
+        assert!(sql.contains("0 = 1"));
+    }
+}
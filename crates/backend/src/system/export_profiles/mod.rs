@@ -0,0 +1,10 @@
+//! Профили выгрузок (`/api/system/export-profiles`).
+//!
+//! Профиль хранит отбор документов, колонки и формат файла. Запуск по кнопке
+//! или по расписанию ставит обычную фоновую выгрузку ([`crate::system::exports`])
+//! от имени владельца профиля; исполнитель расписания ([`service::run_loop`])
+//! раз в минуту запускает профили, время которых наступило.
+
+pub mod extract;
+pub mod repository;
+pub mod service;
@@ -0,0 +1,144 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QueryOrder, Set, Statement,
+};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sys_export_profiles")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub owner_user_id: String,
+    pub organization_id: Option<String>,
+    pub name: String,
+    pub entity_type: String,
+    pub filter_json: String,
+    pub columns_json: String,
+    pub format: String,
+    pub schedule_cron: Option<String>,
+    pub schedule_enabled: bool,
+    pub next_run_at: Option<String>,
+    pub last_run_at: Option<String>,
+    pub last_job_id: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+fn conn() -> &'static DatabaseConnection {
+    get_connection()
+}
+
+pub async fn insert(model: Model) -> Result<(), DbErr> {
+    ActiveModel {
+        id: Set(model.id),
+        owner_user_id: Set(model.owner_user_id),
+        organization_id: Set(model.organization_id),
+        name: Set(model.name),
+        entity_type: Set(model.entity_type),
+        filter_json: Set(model.filter_json),
+        columns_json: Set(model.columns_json),
+        format: Set(model.format),
+        schedule_cron: Set(model.schedule_cron),
+        schedule_enabled: Set(model.schedule_enabled),
+        next_run_at: Set(model.next_run_at),
+        last_run_at: Set(model.last_run_at),
+        last_job_id: Set(model.last_job_id),
+        last_error: Set(model.last_error),
+        created_at: Set(model.created_at),
+        updated_at: Set(model.updated_at),
+    }
+    .insert(conn())
+    .await?;
+    Ok(())
+}
+
+/// Перезаписывает определение профиля; поля последнего запуска не трогает,
+/// организацию — только если она известна.
+pub async fn update_definition(model: &Model) -> Result<(), DbErr> {
+    conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_export_profiles \
+             SET name = ?1, entity_type = ?2, filter_json = ?3, columns_json = ?4, format = ?5, \
+                 schedule_cron = ?6, schedule_enabled = ?7, next_run_at = ?8, updated_at = ?9, \
+                 organization_id = COALESCE(?10, organization_id) \
+             WHERE id = ?11",
+            vec![
+                model.name.clone().into(),
+                model.entity_type.clone().into(),
+                model.filter_json.clone().into(),
+                model.columns_json.clone().into(),
+                model.format.clone().into(),
+                model.schedule_cron.clone().into(),
+                model.schedule_enabled.into(),
+                model.next_run_at.clone().into(),
+                model.updated_at.clone().into(),
+                model.organization_id.clone().into(),
+                model.id.clone().into(),
+            ],
+        ))
+        .await?;
+    Ok(())
+}
+
+pub async fn list_for_user(user_id: &str) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::OwnerUserId.eq(user_id))
+        .order_by_asc(Column::Name)
+        .all(conn())
+        .await
+}
+
+pub async fn get_by_id(id: &str) -> Result<Option<Model>, DbErr> {
+    Entity::find_by_id(id.to_string()).one(conn()).await
+}
+
+/// Профили с включённым расписанием, время запуска которых наступило.
+pub async fn list_due(now: &str) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::ScheduleEnabled.eq(true))
+        .filter(Column::NextRunAt.lte(now))
+        .order_by_asc(Column::NextRunAt)
+        .all(conn())
+        .await
+}
+
+/// Итог постановки запуска: выгрузка (или ошибка) и следующий запуск по расписанию.
+pub async fn set_run_result(
+    id: &str,
+    last_run_at: &str,
+    last_job_id: Option<&str>,
+    last_error: Option<&str>,
+    next_run_at: Option<&str>,
+) -> Result<(), DbErr> {
+    conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_export_profiles \
+             SET last_run_at = ?1, last_job_id = COALESCE(?2, last_job_id), last_error = ?3, \
+                 next_run_at = ?4 \
+             WHERE id = ?5",
+            vec![
+                last_run_at.into(),
+                last_job_id.map(str::to_string).into(),
+                last_error.map(str::to_string).into(),
+                next_run_at.map(str::to_string).into(),
+                id.into(),
+            ],
+        ))
+        .await?;
+    Ok(())
+}
+
+pub async fn delete(id: &str) -> Result<(), DbErr> {
+    Entity::delete_by_id(id.to_string()).exec(conn()).await?;
+    Ok(())
+}
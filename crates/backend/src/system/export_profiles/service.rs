@@ -0,0 +1,499 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use contracts::shared::access::can_view_finance;
+use contracts::system::export_profiles::{
+    entity_info, ExportProfileDto, ExportProfileFilter, ExportProfileListResponse,
+    ExportProfileSaveDto,
+};
+use contracts::system::exports::{ExportFileFormat, ExportJobDto, EXPORT_KIND_PROFILE};
+use uuid::Uuid;
+
+use super::extract::{self, CellValue, FINANCE_COLUMNS};
+use super::repository;
+use crate::shared::export::csv::{decimal, encode_rows};
+use crate::shared::export::xlsx::{encode_workbook, XlsxCell};
+use crate::system::access::resolver;
+use crate::system::exports;
+use crate::system::org_context;
+use crate::system::users;
+
+/// Как часто проверять профили, время запуска которых наступило.
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+fn next_run_from_cron(schedule_cron: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let schedule = cron::Schedule::from_str(schedule_cron).ok()?;
+    schedule.after(&after).next()
+}
+
+fn to_dto(model: repository::Model) -> ExportProfileDto {
+    ExportProfileDto {
+        filter: serde_json::from_str(&model.filter_json).unwrap_or_default(),
+        columns: serde_json::from_str(&model.columns_json).unwrap_or_default(),
+        format: ExportFileFormat::from_code(&model.format).unwrap_or_default(),
+        id: model.id,
+        name: model.name,
+        entity_type: model.entity_type,
+        schedule_cron: model.schedule_cron,
+        schedule_enabled: model.schedule_enabled,
+        next_run_at: model.next_run_at,
+        last_run_at: model.last_run_at,
+        last_job_id: model.last_job_id,
+        last_error: model.last_error,
+        created_at: model.created_at,
+        updated_at: model.updated_at,
+    }
+}
+
+/// Проверка профиля; возвращает следующий запуск по расписанию (`None` — расписание
+/// выключено). Текст ошибки — для пользователя.
+pub fn validate(dto: &ExportProfileSaveDto, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>> {
+    dto.validate().map_err(|e| anyhow!(e))?;
+    let cron = dto
+        .schedule_cron
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty());
+    let Some(cron) = cron else {
+        return Ok(None);
+    };
+    if cron::Schedule::from_str(cron).is_err() {
+        bail!("Некорректное расписание: {}", cron);
+    }
+    if !dto.schedule_enabled {
+        return Ok(None);
+    }
+    match next_run_from_cron(cron, now) {
+        Some(next) => Ok(Some(next)),
+        None => bail!("По расписанию «{}» не будет ни одного запуска", cron),
+    }
+}
+
+fn to_model(
+    id: String,
+    dto: &ExportProfileSaveDto,
+    next_run_at: Option<DateTime<Utc>>,
+    owner_user_id: &str,
+    organization_id: Option<String>,
+    now: &str,
+) -> Result<repository::Model> {
+    Ok(repository::Model {
+        id,
+        owner_user_id: owner_user_id.to_string(),
+        organization_id,
+        name: dto.name.trim().to_string(),
+        entity_type: dto.entity_type.clone(),
+        filter_json: serde_json::to_string(&dto.filter)?,
+        columns_json: serde_json::to_string(&dto.columns)?,
+        format: dto.format.code().to_string(),
+        schedule_cron: dto
+            .schedule_cron
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(str::to_string),
+        schedule_enabled: dto.schedule_enabled,
+        next_run_at: next_run_at.map(|t| t.to_rfc3339()),
+        last_run_at: None,
+        last_job_id: None,
+        last_error: None,
+        created_at: now.to_string(),
+        updated_at: now.to_string(),
+    })
+}
+
+pub async fn list_for_user(user_id: &str) -> Result<ExportProfileListResponse> {
+    Ok(ExportProfileListResponse {
+        items: repository::list_for_user(user_id)
+            .await?
+            .into_iter()
+            .map(to_dto)
+            .collect(),
+    })
+}
+
+async fn get_owned(id: &str, user_id: &str) -> Result<Option<repository::Model>> {
+    Ok(repository::get_by_id(id)
+        .await?
+        .filter(|p| p.owner_user_id == user_id))
+}
+
+/// Создаёт профиль. Запрос должен пройти [`validate`].
+pub async fn create(
+    dto: &ExportProfileSaveDto,
+    next_run_at: Option<DateTime<Utc>>,
+    user_id: &str,
+    organization_id: Option<String>,
+) -> Result<ExportProfileDto> {
+    let now = Utc::now().to_rfc3339();
+    let model = to_model(
+        Uuid::new_v4().to_string(),
+        dto,
+        next_run_at,
+        user_id,
+        organization_id,
+        &now,
+    )?;
+    repository::insert(model.clone()).await?;
+    Ok(to_dto(model))
+}
+
+/// Изменяет профиль пользователя; `None` — профиля нет или он чужой.
+pub async fn update(
+    id: &str,
+    dto: &ExportProfileSaveDto,
+    next_run_at: Option<DateTime<Utc>>,
+    user_id: &str,
+    organization_id: Option<String>,
+) -> Result<Option<ExportProfileDto>> {
+    let Some(existing) = get_owned(id, user_id).await? else {
+        return Ok(None);
+    };
+    let now = Utc::now().to_rfc3339();
+    let mut model = to_model(
+        existing.id,
+        dto,
+        next_run_at,
+        user_id,
+        organization_id,
+        &now,
+    )?;
+    repository::update_definition(&model).await?;
+    model.organization_id = model.organization_id.or(existing.organization_id);
+    model.created_at = existing.created_at;
+    model.last_run_at = existing.last_run_at;
+    model.last_job_id = existing.last_job_id;
+    model.last_error = existing.last_error;
+    Ok(Some(to_dto(model)))
+}
+
+pub async fn delete(id: &str, user_id: &str) -> Result<bool> {
+    if get_owned(id, user_id).await?.is_none() {
+        return Ok(false);
+    }
+    repository::delete(id).await?;
+    Ok(true)
+}
+
+/// Права владельца на момент запуска.
+/// Проверяются при каждом запуске — роль могла измениться после сохранения профиля.
+struct OwnerAccess {
+    can_read: bool,
+    show_finance: bool,
+    /// Назначенные организации; `None` — все (администратор).
+    organizations: Option<Vec<String>>,
+}
+
+async fn owner_access(owner_user_id: &str, entity_type: &str) -> Result<OwnerAccess> {
+    let denied = OwnerAccess {
+        can_read: false,
+        show_finance: false,
+        organizations: Some(Vec::new()),
+    };
+    let Some(user) = users::service::get_by_id(owner_user_id).await? else {
+        return Ok(denied);
+    };
+    if !user.is_active {
+        return Ok(denied);
+    }
+    if user.is_admin {
+        return Ok(OwnerAccess {
+            can_read: true,
+            show_finance: true,
+            organizations: None,
+        });
+    }
+    let scopes = resolver::resolve_user_scopes(owner_user_id).await?;
+    Ok(OwnerAccess {
+        can_read: scopes.iter().any(|s| s.scope_id == entity_type),
+        show_finance: can_view_finance(false, &scopes),
+        organizations: Some(org_context::service::member_organizations(owner_user_id).await?),
+    })
+}
+
+/// Число в CSV: целые — без дробной части (nmID), остальное — с запятой.
+fn csv_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        decimal(value)
+    }
+}
+
+/// Файл профиля в выбранном формате.
+pub fn encode(
+    format: ExportFileFormat,
+    sheet: &str,
+    keys: &[String],
+    headers: &[String],
+    rows: &[Vec<CellValue>],
+) -> Result<Vec<u8>> {
+    match format {
+        ExportFileFormat::Csv => {
+            // UTF-8 BOM — корректная кириллица при открытии в Excel.
+            let mut buffer = "\u{FEFF}".as_bytes().to_vec();
+            let mut lines = Vec::with_capacity(rows.len() + 1);
+            lines.push(headers.to_vec());
+            lines.extend(rows.iter().map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        CellValue::Text(text) => text.clone(),
+                        CellValue::Number(value) => csv_number(*value),
+                        CellValue::Bool(true) => "Да".to_string(),
+                        CellValue::Bool(false) => "Нет".to_string(),
+                        CellValue::Null => String::new(),
+                    })
+                    .collect()
+            }));
+            buffer.extend(encode_rows(&lines)?);
+            Ok(buffer)
+        }
+        ExportFileFormat::Xlsx => {
+            let cells: Vec<Vec<XlsxCell>> = rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|cell| match cell {
+                            CellValue::Text(text) => XlsxCell::Text(text.clone()),
+                            CellValue::Number(value) => XlsxCell::Number(*value),
+                            CellValue::Bool(true) => XlsxCell::Text("Да".to_string()),
+                            CellValue::Bool(false) => XlsxCell::Text("Нет".to_string()),
+                            CellValue::Null => XlsxCell::Empty,
+                        })
+                        .collect()
+                })
+                .collect();
+            encode_workbook(sheet, headers, &cells)
+        }
+        ExportFileFormat::Ndjson => {
+            let mut buffer = Vec::new();
+            for row in rows {
+                let object: serde_json::Map<String, serde_json::Value> = keys
+                    .iter()
+                    .zip(row)
+                    .map(|(key, cell)| {
+                        let value = match cell {
+                            CellValue::Text(text) => serde_json::Value::from(text.clone()),
+                            CellValue::Number(value) if value.fract() == 0.0 => {
+                                serde_json::Value::from(*value as i64)
+                            }
+                            CellValue::Number(value) => serde_json::Value::from(*value),
+                            CellValue::Bool(value) => serde_json::Value::from(*value),
+                            CellValue::Null => serde_json::Value::Null,
+                        };
+                        (key.clone(), value)
+                    })
+                    .collect();
+                serde_json::to_writer(&mut buffer, &object)?;
+                buffer.push(b'\n');
+            }
+            Ok(buffer)
+        }
+    }
+}
+
+/// Формирует файл профиля: читает документы организаций владельца, скрывает суммы
+/// без доступа к финансам.
+async fn build_file(profile: repository::Model, access: OwnerAccess) -> Result<(Vec<u8>, usize)> {
+    let entity = entity_info(&profile.entity_type)
+        .ok_or_else(|| anyhow!("Неизвестный тип документов: {}", profile.entity_type))?;
+    let filter: ExportProfileFilter = serde_json::from_str(&profile.filter_json)?;
+    let keys: Vec<String> = serde_json::from_str(&profile.columns_json)?;
+    let format = ExportFileFormat::from_code(&profile.format).unwrap_or_default();
+
+    let mut rows = extract::fetch_rows(
+        entity.entity_type,
+        &filter,
+        &keys,
+        access.organizations.as_deref(),
+        Utc::now().date_naive(),
+    )
+    .await?;
+    if !access.show_finance {
+        let hidden: Vec<usize> = keys
+            .iter()
+            .enumerate()
+            .filter(|(_, key)| FINANCE_COLUMNS.contains(&key.as_str()))
+            .map(|(i, _)| i)
+            .collect();
+        for row in &mut rows {
+            for &i in &hidden {
+                row[i] = CellValue::Null;
+            }
+        }
+    }
+    let headers: Vec<String> = keys
+        .iter()
+        .map(|key| {
+            entity
+                .columns
+                .iter()
+                .find(|c| c.key == key.as_str())
+                .map(|c| c.label.to_string())
+                .unwrap_or_else(|| key.clone())
+        })
+        .collect();
+    let buffer = encode(format, entity.label, &keys, &headers, &rows)?;
+    Ok((buffer, rows.len()))
+}
+
+/// Ставит выгрузку по профилю от имени владельца.
+async fn start(profile: &repository::Model) -> Result<ExportJobDto> {
+    let access = owner_access(&profile.owner_user_id, &profile.entity_type).await?;
+    if !access.can_read {
+        bail!("Нет доступа к документам профиля");
+    }
+    let format = ExportFileFormat::from_code(&profile.format).unwrap_or_default();
+    let title = format!("Профиль «{}» ({})", profile.name, format.label());
+    let file_stem = format!("profile_{}", profile.entity_type);
+    exports::service::start(
+        EXPORT_KIND_PROFILE,
+        title,
+        file_stem,
+        format,
+        &profile.owner_user_id,
+        profile.organization_id.clone(),
+        build_file(profile.clone(), access),
+    )
+    .await
+}
+
+/// Запуск профиля по кнопке; `None` — профиля нет или он чужой.
+pub async fn run(id: &str, user_id: &str) -> Result<Option<ExportJobDto>> {
+    let Some(profile) = get_owned(id, user_id).await? else {
+        return Ok(None);
+    };
+    let now = Utc::now().to_rfc3339();
+    match start(&profile).await {
+        Ok(job) => {
+            repository::set_run_result(
+                &profile.id,
+                &now,
+                Some(&job.id),
+                None,
+                profile.next_run_at.as_deref(),
+            )
+            .await?;
+            Ok(Some(job))
+        }
+        Err(e) => {
+            repository::set_run_result(
+                &profile.id,
+                &now,
+                None,
+                Some(&e.to_string()),
+                profile.next_run_at.as_deref(),
+            )
+            .await?;
+            Err(e)
+        }
+    }
+}
+
+/// Запускает профили, время которых наступило. Возвращает число запусков.
+pub async fn run_due() -> Result<usize> {
+    let now = Utc::now();
+    let due = repository::list_due(&now.to_rfc3339()).await?;
+    for profile in &due {
+        let outcome = start(profile).await;
+        let next_run_at = profile
+            .schedule_cron
+            .as_deref()
+            .and_then(|cron| next_run_from_cron(cron, now))
+            .map(|t| t.to_rfc3339());
+        let (job_id, error) = match &outcome {
+            Ok(job) => (Some(job.id.as_str()), None),
+            Err(e) => {
+                tracing::warn!("[export_profiles] profile {} failed: {e}", profile.id);
+                (None, Some(e.to_string()))
+            }
+        };
+        repository::set_run_result(
+            &profile.id,
+            &now.to_rfc3339(),
+            job_id,
+            error.as_deref(),
+            next_run_at.as_deref(),
+        )
+        .await?;
+    }
+    Ok(due.len())
+}
+
+/// Исполнитель расписаний профилей. Как и отложенное проведение — не регламентное
+/// задание: расписание задаёт владелец профиля, а не администратор.
+pub async fn run_loop() {
+    loop {
+        match run_due().await {
+            Ok(n) if n > 0 => tracing::info!("[export_profiles] started {n} scheduled export(s)"),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("[export_profiles] run failed: {e}"),
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn dto() -> ExportProfileSaveDto {
+        ExportProfileSaveDto {
+            name: "Партнёр X".to_string(),
+            entity_type: "a012_wb_sales".to_string(),
+            columns: vec!["document_no".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn validate_computes_next_run_only_for_enabled_schedule() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        assert_eq!(validate(&dto(), now).unwrap(), None);
+
+        let mut scheduled = dto();
+        scheduled.schedule_cron = Some("0 0 7 * * Mon".to_string());
+        assert_eq!(validate(&scheduled, now).unwrap(), None);
+        scheduled.schedule_enabled = true;
+        assert_eq!(
+            validate(&scheduled, now).unwrap(),
+            Some(Utc.with_ymd_and_hms(2026, 10, 19, 7, 0, 0).unwrap())
+        );
+
+        scheduled.schedule_cron = Some("каждый день".to_string());
+        assert!(validate(&scheduled, now).is_err());
+    }
+
+    #[test]
+    fn encode_formats_cells_per_format() {
+        let keys = vec![
+            "nm_id".to_string(),
+            "price".to_string(),
+            "posted".to_string(),
+        ];
+        let headers = vec![
+            "nmID".to_string(),
+            "Цена".to_string(),
+            "Проведён".to_string(),
+        ];
+        let rows = vec![vec![
+            CellValue::Number(123456.0),
+            CellValue::Number(99.5),
+            CellValue::Bool(true),
+        ]];
+
+        let csv = encode(ExportFileFormat::Csv, "s", &keys, &headers, &rows).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with('\u{FEFF}'));
+        assert!(csv.ends_with("123456;99,50;Да\n"));
+
+        let ndjson = encode(ExportFileFormat::Ndjson, "s", &keys, &headers, &rows).unwrap();
+        assert_eq!(
+            String::from_utf8(ndjson).unwrap(),
+            "{\"nm_id\":123456,\"price\":99.5,\"posted\":true}\n"
+        );
+    }
+}
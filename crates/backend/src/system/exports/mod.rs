@@ -0,0 +1,9 @@
+//! Фоновые выгрузки («Мои выгрузки»).
+//!
+//! Тяжёлый экспорт (например, финансовый отчёт WB за год) не укладывается в таймаут
+//! HTTP-запроса, поэтому хендлер только ставит задачу: она формирует файл в фоне,
+//! сохраняет его в S3 (категория `exports`) и уведомляет владельца через диспетчер
+//! уведомлений. Файл доступен до `expires_at`, затем удаляется циклом очистки.
+
+pub mod repository;
+pub mod service;
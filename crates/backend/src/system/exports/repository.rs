@@ -0,0 +1,166 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    Statement,
+};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sys_export_jobs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub user_id: String,
+    pub kind: String,
+    pub title: String,
+    pub status: String,
+    pub file_id: Option<String>,
+    pub filename: Option<String>,
+    pub size_bytes: Option<i64>,
+    pub row_count: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub finished_at: Option<String>,
+    pub expires_at: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+fn conn() -> &'static DatabaseConnection {
+    get_connection()
+}
+
+pub async fn insert(model: Model) -> Result<(), DbErr> {
+    ActiveModel {
+        id: Set(model.id),
+        user_id: Set(model.user_id),
+        kind: Set(model.kind),
+        title: Set(model.title),
+        status: Set(model.status),
+        file_id: Set(model.file_id),
+        filename: Set(model.filename),
+        size_bytes: Set(model.size_bytes),
+        row_count: Set(model.row_count),
+        error: Set(model.error),
+        created_at: Set(model.created_at),
+        finished_at: Set(model.finished_at),
+        expires_at: Set(model.expires_at),
+    }
+    .insert(conn())
+    .await?;
+    Ok(())
+}
+
+pub async fn list_for_user(user_id: &str, limit: u64) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .order_by_desc(Column::CreatedAt)
+        .limit(limit)
+        .all(conn())
+        .await
+}
+
+pub async fn get_by_id(id: &str) -> Result<Option<Model>, DbErr> {
+    Entity::find_by_id(id.to_string()).one(conn()).await
+}
+
+pub async fn set_status(id: &str, status: &str) -> Result<(), DbErr> {
+    conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_export_jobs SET status = ?1 WHERE id = ?2",
+            vec![status.into(), id.into()],
+        ))
+        .await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn finish_done(
+    id: &str,
+    status: &str,
+    file_id: &str,
+    filename: &str,
+    size_bytes: i64,
+    row_count: i64,
+    finished_at: &str,
+    expires_at: &str,
+) -> Result<(), DbErr> {
+    conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_export_jobs \
+             SET status = ?1, file_id = ?2, filename = ?3, size_bytes = ?4, row_count = ?5, \
+                 finished_at = ?6, expires_at = ?7, error = NULL \
+             WHERE id = ?8",
+            vec![
+                status.into(),
+                file_id.into(),
+                filename.into(),
+                size_bytes.into(),
+                row_count.into(),
+                finished_at.into(),
+                expires_at.into(),
+                id.into(),
+            ],
+        ))
+        .await?;
+    Ok(())
+}
+
+pub async fn finish_failed(
+    id: &str,
+    status: &str,
+    error: &str,
+    finished_at: &str,
+) -> Result<(), DbErr> {
+    conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_export_jobs SET status = ?1, error = ?2, finished_at = ?3 WHERE id = ?4",
+            vec![status.into(), error.into(), finished_at.into(), id.into()],
+        ))
+        .await?;
+    Ok(())
+}
+
+/// Незавершённые задачи предыдущего процесса сервера → `failed`.
+pub async fn fail_unfinished(
+    unfinished: &[&str],
+    status: &str,
+    error: &str,
+    finished_at: &str,
+) -> Result<u64, DbErr> {
+    let placeholders = vec!["?"; unfinished.len()].join(", ");
+    let mut values: Vec<sea_orm::Value> = vec![status.into(), error.into(), finished_at.into()];
+    values.extend(unfinished.iter().map(|s| (*s).into()));
+    let result = conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            &format!(
+                "UPDATE sys_export_jobs SET status = ?, error = ?, finished_at = ? \
+                 WHERE status IN ({placeholders})"
+            ),
+            values,
+        ))
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Готовые выгрузки с истёкшим сроком хранения.
+pub async fn list_expired(status: &str, now: &str) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::Status.eq(status))
+        .filter(Column::ExpiresAt.lte(now))
+        .all(conn())
+        .await
+}
+
+pub async fn delete(id: &str) -> Result<(), DbErr> {
+    Entity::delete_by_id(id.to_string()).exec(conn()).await?;
+    Ok(())
+}
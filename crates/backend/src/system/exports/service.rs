@@ -0,0 +1,288 @@
+use std::future::Future;
+
+use anyhow::Result;
+use bytes::Bytes;
+use chrono::{Duration, Utc};
+use contracts::system::exports::{
+    ExportFileFormat, ExportJobDto, ExportJobListResponse, ExportJobStatus, EXPORT_TTL_DAYS,
+};
+use contracts::system::notifications::NotificationEvent;
+use contracts::system::s3::S3FileCategory;
+use once_cell::sync::Lazy;
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+
+use super::repository;
+use crate::shared::config::get_mail_config;
+use crate::system::notifications::dispatcher::{self, Notification};
+use crate::system::quotas::service as quotas;
+use crate::system::s3::service::{self as s3, DownloadedFile, UploadedFile};
+
+/// Одновременно формируемых выгрузок; остальные ждут в статусе «В очереди».
+const MAX_CONCURRENT_EXPORTS: usize = 2;
+const LIST_LIMIT: u64 = 100;
+/// Вкладка «Мои выгрузки» — открывается по клику на уведомление.
+const EXPORTS_TAB_KEY: &str = "sys_exports";
+
+static EXPORT_SLOTS: Lazy<Semaphore> = Lazy::new(|| Semaphore::new(MAX_CONCURRENT_EXPORTS));
+
+fn to_dto(model: repository::Model) -> ExportJobDto {
+    ExportJobDto {
+        status: ExportJobStatus::from_code(&model.status).unwrap_or(ExportJobStatus::Failed),
+        id: model.id,
+        kind: model.kind,
+        title: model.title,
+        filename: model.filename,
+        size_bytes: model.size_bytes,
+        row_count: model.row_count,
+        error: model.error,
+        created_at: model.created_at,
+        finished_at: model.finished_at,
+        expires_at: model.expires_at,
+    }
+}
+
+/// Ставит выгрузку в очередь и сразу возвращает задачу. `build` формирует файл
+/// в формате `format` и возвращает содержимое и число строк; `file_stem` — начало
+/// имени файла. Строки учитываются в месячной квоте `organization_id`; при
+/// исчерпанной квоте — ошибка `QuotaExceeded` ещё до постановки в очередь.
+#[allow(clippy::too_many_arguments)]
+pub async fn start<F>(
+    kind: &str,
+    title: String,
+    file_stem: String,
+    format: ExportFileFormat,
+    user_id: &str,
+    organization_id: Option<String>,
+    build: F,
+) -> Result<ExportJobDto>
+where
+    F: Future<Output = Result<(Vec<u8>, usize)>> + Send + 'static,
+{
+    if let Some(organization_id) = organization_id.as_deref() {
+        quotas::check_export(organization_id).await?;
+    }
+    let model = repository::Model {
+        id: Uuid::new_v4().to_string(),
+        user_id: user_id.to_string(),
+        kind: kind.to_string(),
+        title,
+        status: ExportJobStatus::Queued.code().to_string(),
+        file_id: None,
+        filename: None,
+        size_bytes: None,
+        row_count: None,
+        error: None,
+        created_at: Utc::now().to_rfc3339(),
+        finished_at: None,
+        expires_at: None,
+    };
+    repository::insert(model.clone()).await?;
+
+    let job = model.clone();
+    tokio::spawn(async move {
+        run(job, &file_stem, format, organization_id, build).await;
+    });
+
+    Ok(to_dto(model))
+}
+
+async fn run<F>(
+    job: repository::Model,
+    file_stem: &str,
+    format: ExportFileFormat,
+    organization_id: Option<String>,
+    build: F,
+) where
+    F: Future<Output = Result<(Vec<u8>, usize)>>,
+{
+    let Ok(_slot) = EXPORT_SLOTS.acquire().await else {
+        return;
+    };
+    if let Err(e) = repository::set_status(&job.id, ExportJobStatus::Running.code()).await {
+        tracing::warn!("[export {}] failed to mark running: {e}", job.id);
+    }
+
+    let started = std::time::Instant::now();
+    let filename = format!(
+        "{}_{}.{}",
+        file_stem,
+        Utc::now().format("%Y%m%d_%H%M%S"),
+        format.extension()
+    );
+    let outcome = async {
+        let (buffer, rows) = build.await?;
+        if let Some(organization_id) = organization_id.as_deref() {
+            quotas::register_export_rows(organization_id, rows).await?;
+        }
+        let size = buffer.len() as i64;
+        let file = s3::store_generated(
+            S3FileCategory::Exports,
+            UploadedFile {
+                filename: filename.clone(),
+                content_type: Some(format.content_type().to_string()),
+                bytes: Bytes::from(buffer),
+            },
+            Some(job.user_id.clone()),
+        )
+        .await?;
+        let now = Utc::now();
+        repository::finish_done(
+            &job.id,
+            ExportJobStatus::Done.code(),
+            &file.id,
+            &filename,
+            size,
+            rows as i64,
+            &now.to_rfc3339(),
+            &(now + Duration::days(EXPORT_TTL_DAYS)).to_rfc3339(),
+        )
+        .await?;
+        anyhow::Ok(rows)
+    }
+    .await;
+
+    let (title, body) = match outcome {
+        Ok(rows) => {
+            tracing::info!(
+                "[export {}] {} done: {} rows in {:.1}s",
+                job.id,
+                job.kind,
+                rows,
+                started.elapsed().as_secs_f64()
+            );
+            (
+                "Выгрузка готова".to_string(),
+                format!(
+                    "{}: {} строк. Файл доступен в «Мои выгрузки» {} дн.",
+                    job.title, rows, EXPORT_TTL_DAYS
+                ),
+            )
+        }
+        Err(e) => {
+            tracing::error!("[export {}] {} failed: {e}", job.id, job.kind);
+            let finished_at = Utc::now().to_rfc3339();
+            if let Err(db_err) = repository::finish_failed(
+                &job.id,
+                ExportJobStatus::Failed.code(),
+                &e.to_string(),
+                &finished_at,
+            )
+            .await
+            {
+                tracing::warn!("[export {}] failed to mark failed: {db_err}", job.id);
+            }
+            (
+                "Выгрузка не сформирована".to_string(),
+                format!("{}: {}", job.title, e),
+            )
+        }
+    };
+
+    let base = get_mail_config()
+        .base_url
+        .trim()
+        .trim_end_matches('/')
+        .to_string();
+    let notification = Notification {
+        event: NotificationEvent::ExportReady,
+        title,
+        body,
+        tab_key: Some(EXPORTS_TAB_KEY.to_string()),
+        link: (!base.is_empty()).then(|| format!("{base}/?active={EXPORTS_TAB_KEY}")),
+    };
+    let report = dispatcher::dispatch(&notification, &[job.user_id.clone()]).await;
+    if report.failed > 0 {
+        tracing::warn!(
+            "[export {}] notification: {} deliveries failed",
+            job.id,
+            report.failed
+        );
+    }
+}
+
+pub async fn list_for_user(user_id: &str) -> Result<ExportJobListResponse> {
+    Ok(ExportJobListResponse {
+        items: repository::list_for_user(user_id, LIST_LIMIT)
+            .await?
+            .into_iter()
+            .map(to_dto)
+            .collect(),
+    })
+}
+
+/// Файл готовой выгрузки; `None` — нет такой задачи у пользователя или файл уже удалён.
+pub async fn download(id: &str, user_id: &str) -> Result<Option<DownloadedFile>> {
+    let Some(job) = repository::get_by_id(id).await? else {
+        return Ok(None);
+    };
+    if job.user_id != user_id || job.status != ExportJobStatus::Done.code() {
+        return Ok(None);
+    }
+    let Some(file_id) = job.file_id.as_deref() else {
+        return Ok(None);
+    };
+    s3::download(file_id).await
+}
+
+/// Удаляет выгрузку пользователя вместе с файлом. Незавершённую задачу удалить нельзя.
+pub async fn delete(id: &str, user_id: &str) -> Result<bool> {
+    let Some(job) = repository::get_by_id(id).await? else {
+        return Ok(false);
+    };
+    let unfinished = matches!(
+        ExportJobStatus::from_code(&job.status),
+        Some(ExportJobStatus::Queued | ExportJobStatus::Running)
+    );
+    if job.user_id != user_id || unfinished {
+        return Ok(false);
+    }
+    if let Some(file_id) = job.file_id.as_deref() {
+        s3::delete(file_id).await?;
+    }
+    repository::delete(id).await?;
+    Ok(true)
+}
+
+/// Задачи, которые формировались при остановке сервера, уже не завершатся.
+pub async fn fail_interrupted() -> Result<u64> {
+    Ok(repository::fail_unfinished(
+        &[
+            ExportJobStatus::Queued.code(),
+            ExportJobStatus::Running.code(),
+        ],
+        ExportJobStatus::Failed.code(),
+        "Прервано перезапуском сервера",
+        &Utc::now().to_rfc3339(),
+    )
+    .await?)
+}
+
+/// Удаляет файлы выгрузок с истёкшим сроком хранения; запись остаётся со статусом «Срок истёк».
+pub async fn purge_expired() -> Result<usize> {
+    let now = Utc::now().to_rfc3339();
+    let expired = repository::list_expired(ExportJobStatus::Done.code(), &now).await?;
+    let mut purged = 0;
+    for job in expired {
+        if let Some(file_id) = job.file_id.as_deref() {
+            if let Err(e) = s3::delete(file_id).await {
+                tracing::warn!("[exports] failed to delete expired file {file_id}: {e}");
+                continue;
+            }
+        }
+        repository::set_status(&job.id, ExportJobStatus::Expired.code()).await?;
+        purged += 1;
+    }
+    Ok(purged)
+}
+
+pub async fn run_purge_loop() {
+    loop {
+        match purge_expired().await {
+            Ok(n) if n > 0 => tracing::info!("[exports] purged {n} expired export file(s)"),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("[exports] purge failed: {e}"),
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(60 * 60)).await;
+    }
+}
@@ -0,0 +1,7 @@
+//! Внешние ссылки документов (`/api/external-refs`).
+//!
+//! Ссылка хранится в `sys_external_refs` и привязана к документу по
+//! `entity_type` + `document_id`; глобальный поиск ищет по ключу, системе и URL.
+
+pub mod repository;
+pub mod service;
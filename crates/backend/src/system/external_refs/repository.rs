@@ -0,0 +1,113 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sys_external_refs")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub entity_type: String,
+    pub document_id: String,
+    pub system: String,
+    pub ref_key: String,
+    pub url: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+fn conn() -> &'static DatabaseConnection {
+    get_connection()
+}
+
+pub async fn insert(model: Model) -> Result<(), DbErr> {
+    ActiveModel {
+        id: Set(model.id),
+        entity_type: Set(model.entity_type),
+        document_id: Set(model.document_id),
+        system: Set(model.system),
+        ref_key: Set(model.ref_key),
+        url: Set(model.url),
+        created_by: Set(model.created_by),
+        created_at: Set(model.created_at),
+        updated_at: Set(model.updated_at),
+    }
+    .insert(conn())
+    .await?;
+    Ok(())
+}
+
+pub async fn update(model: Model) -> Result<(), DbErr> {
+    ActiveModel {
+        id: Set(model.id),
+        system: Set(model.system),
+        ref_key: Set(model.ref_key),
+        url: Set(model.url),
+        updated_at: Set(model.updated_at),
+        ..Default::default()
+    }
+    .update(conn())
+    .await?;
+    Ok(())
+}
+
+pub async fn get_by_id(id: &str) -> Result<Option<Model>, DbErr> {
+    Entity::find_by_id(id.to_string()).one(conn()).await
+}
+
+pub async fn delete(id: &str) -> Result<u64, DbErr> {
+    Ok(Entity::delete_by_id(id.to_string())
+        .exec(conn())
+        .await?
+        .rows_affected)
+}
+
+pub async fn list_for_document(entity_type: &str, document_id: &str) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::EntityType.eq(entity_type))
+        .filter(Column::DocumentId.eq(document_id))
+        .order_by_asc(Column::CreatedAt)
+        .all(conn())
+        .await
+}
+
+/// Ссылка документа с той же системой и ключом, кроме `exclude_id`.
+pub async fn find_duplicate(
+    entity_type: &str,
+    document_id: &str,
+    system: &str,
+    ref_key: &str,
+    exclude_id: Option<&str>,
+) -> Result<Option<Model>, DbErr> {
+    let mut query = Entity::find()
+        .filter(Column::EntityType.eq(entity_type))
+        .filter(Column::DocumentId.eq(document_id))
+        .filter(Column::System.eq(system))
+        .filter(Column::RefKey.eq(ref_key));
+    if let Some(id) = exclude_id {
+        query = query.filter(Column::Id.ne(id));
+    }
+    query.one(conn()).await
+}
+
+/// Подстрока в ключе, системе или URL; свежие — первыми.
+pub async fn search(text: &str, limit: u64) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(
+            Condition::any()
+                .add(Column::RefKey.contains(text))
+                .add(Column::System.contains(text))
+                .add(Column::Url.contains(text)),
+        )
+        .order_by_desc(Column::UpdatedAt)
+        .limit(limit)
+        .all(conn())
+        .await
+}
@@ -0,0 +1,185 @@
+use anyhow::{anyhow, bail, Result};
+use chrono::Utc;
+use contracts::system::external_refs::{
+    external_ref_document, CreateExternalRefRequest, ExternalRefDto, ExternalRefFields,
+};
+use uuid::Uuid;
+
+use super::repository;
+use crate::shared::data::projection_archive;
+use crate::system::document_audit;
+use crate::system::org_context::ActiveOrganization;
+
+/// Результатов глобального поиска.
+const SEARCH_LIMIT: u64 = 100;
+
+fn to_dto(model: repository::Model) -> ExternalRefDto {
+    ExternalRefDto {
+        id: model.id,
+        entity_type: model.entity_type,
+        document_id: model.document_id,
+        system: model.system,
+        key: model.ref_key,
+        url: model.url,
+        created_by: model.created_by,
+        created_at: model.created_at,
+        updated_at: model.updated_at,
+    }
+}
+
+fn validate_document(entity_type: &str, document_id: &str) -> Result<()> {
+    if external_ref_document(entity_type).is_none() {
+        bail!("Внешние ссылки не поддерживаются для '{}'", entity_type);
+    }
+    if Uuid::parse_str(document_id).is_err() {
+        bail!("Некорректный id документа");
+    }
+    Ok(())
+}
+
+/// Документ ссылки есть в организации запроса; документ другой организации — как
+/// несуществующий.
+async fn ensure_document_visible(
+    active_org: &ActiveOrganization,
+    entity_type: &str,
+    document_id: &str,
+) -> Result<()> {
+    if active_org.0.is_none() {
+        return Ok(());
+    }
+    let document =
+        external_ref_document(entity_type).ok_or_else(|| anyhow!("Document not found"))?;
+    let source = projection_archive::source(document.entity_type, None);
+    let visible = document_audit::repository::snapshot(&source, document_id)
+        .await
+        .and_then(|row| document_audit::row_organization(&row))
+        .is_some_and(|organization_id| active_org.allows(&organization_id));
+    if !visible {
+        bail!("Document not found");
+    }
+    Ok(())
+}
+
+async fn ensure_unique(
+    entity_type: &str,
+    document_id: &str,
+    fields: &ExternalRefFields,
+    exclude_id: Option<&str>,
+) -> Result<()> {
+    let duplicate = repository::find_duplicate(
+        entity_type,
+        document_id,
+        &fields.system,
+        &fields.key,
+        exclude_id,
+    )
+    .await?;
+    if duplicate.is_some() {
+        bail!(
+            "Ссылка {} {} у документа уже есть",
+            fields.system,
+            fields.key
+        );
+    }
+    Ok(())
+}
+
+pub async fn list_for_document(
+    active_org: &ActiveOrganization,
+    entity_type: &str,
+    document_id: &str,
+) -> Result<Vec<ExternalRefDto>> {
+    ensure_document_visible(active_org, entity_type, document_id).await?;
+    Ok(repository::list_for_document(entity_type, document_id)
+        .await?
+        .into_iter()
+        .map(to_dto)
+        .collect())
+}
+
+/// Ошибки проверки — `Invalid request: ...` (текст после префикса — для пользователя).
+pub async fn create(
+    active_org: &ActiveOrganization,
+    request: &CreateExternalRefRequest,
+    username: &str,
+) -> Result<ExternalRefDto> {
+    validate_document(&request.entity_type, &request.document_id)
+        .map_err(|e| anyhow!("Invalid request: {e}"))?;
+    ensure_document_visible(active_org, &request.entity_type, &request.document_id).await?;
+    let fields = request
+        .fields
+        .normalized()
+        .map_err(|e| anyhow!("Invalid request: {e}"))?;
+    ensure_unique(&request.entity_type, &request.document_id, &fields, None)
+        .await
+        .map_err(|e| anyhow!("Invalid request: {e}"))?;
+
+    let now = Utc::now().to_rfc3339();
+    let model = repository::Model {
+        id: Uuid::new_v4().to_string(),
+        entity_type: request.entity_type.clone(),
+        document_id: request.document_id.clone(),
+        system: fields.system,
+        ref_key: fields.key,
+        url: fields.url,
+        created_by: username.to_string(),
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    repository::insert(model.clone()).await?;
+    Ok(to_dto(model))
+}
+
+pub async fn update(
+    active_org: &ActiveOrganization,
+    id: &str,
+    fields: &ExternalRefFields,
+) -> Result<ExternalRefDto> {
+    let mut model = repository::get_by_id(id)
+        .await?
+        .ok_or_else(|| anyhow!("External reference not found"))?;
+    ensure_document_visible(active_org, &model.entity_type, &model.document_id).await?;
+    let fields = fields
+        .normalized()
+        .map_err(|e| anyhow!("Invalid request: {e}"))?;
+    ensure_unique(&model.entity_type, &model.document_id, &fields, Some(id))
+        .await
+        .map_err(|e| anyhow!("Invalid request: {e}"))?;
+
+    model.system = fields.system;
+    model.ref_key = fields.key;
+    model.url = fields.url;
+    model.updated_at = Utc::now().to_rfc3339();
+    repository::update(model.clone()).await?;
+    Ok(to_dto(model))
+}
+
+pub async fn delete(active_org: &ActiveOrganization, id: &str) -> Result<()> {
+    let model = repository::get_by_id(id)
+        .await?
+        .ok_or_else(|| anyhow!("External reference not found"))?;
+    ensure_document_visible(active_org, &model.entity_type, &model.document_id).await?;
+    if repository::delete(id).await? == 0 {
+        bail!("External reference not found");
+    }
+    Ok(())
+}
+
+/// Глобальный поиск по ключу, системе и URL; пустой запрос — пустой результат.
+/// Ссылки документов других организаций не возвращаются.
+pub async fn search(active_org: &ActiveOrganization, text: &str) -> Result<Vec<ExternalRefDto>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut result = Vec::new();
+    for model in repository::search(text, SEARCH_LIMIT).await? {
+        if ensure_document_visible(active_org, &model.entity_type, &model.document_id)
+            .await
+            .is_ok()
+        {
+            result.push(to_dto(model));
+        }
+    }
+    Ok(result)
+}
@@ -0,0 +1,220 @@
+//! Проверки живости и готовности сервера для reverse proxy и мониторинга.
+//!
+//! - `/healthz` — всегда 200, в теле статус каждой проверки и её длительность;
+//! - `/readyz` — 503, пока не пройдены критичные проверки (старт не завершён,
+//!   БД не отвечает), чтобы прокси не направлял трафик на этот экземпляр.
+//!
+//! Воркер регламентных заданий сообщает о каждом такте через [`worker_heartbeat`];
+//! его отставание не критично для готовности и отражается только в отчёте.
+
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use contracts::system::health::{HealthCheckDto, HealthReportDto, HealthStatus};
+use once_cell::sync::Lazy;
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+use crate::shared::config::get_scheduler_config_enabled;
+use crate::shared::data::db::get_connection;
+
+/// Таймаут проверки БД: зависший SQLite не должен подвешивать пробу прокси.
+const DB_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Сколько пропущенных тактов воркера считаем его зависшим.
+const WORKER_MISSED_TICKS: i64 = 3;
+
+static STARTED_AT: Lazy<Instant> = Lazy::new(Instant::now);
+static STARTUP_COMPLETE: AtomicBool = AtomicBool::new(false);
+/// Интервал воркера в секундах; 0 — воркер не запущен.
+static WORKER_INTERVAL_SECS: AtomicU64 = AtomicU64::new(0);
+/// Unix-время последнего такта воркера; 0 — тактов ещё не было.
+static WORKER_LAST_TICK: AtomicI64 = AtomicI64::new(0);
+
+/// Вызывается, когда HTTP-сервер готов принимать запросы.
+pub fn mark_started() {
+    Lazy::force(&STARTED_AT);
+    STARTUP_COMPLETE.store(true, Ordering::SeqCst);
+}
+
+/// Воркер регламентных заданий запущен с заданным интервалом.
+pub fn worker_started(interval_secs: u64) {
+    WORKER_INTERVAL_SECS.store(interval_secs, Ordering::SeqCst);
+}
+
+/// Очередной такт воркера.
+pub fn worker_heartbeat() {
+    WORKER_LAST_TICK.store(Utc::now().timestamp(), Ordering::SeqCst);
+}
+
+fn elapsed_ms(start: Instant) -> u64 {
+    start.elapsed().as_millis() as u64
+}
+
+async fn check_database() -> HealthCheckDto {
+    let start = Instant::now();
+    let query = get_connection().execute(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "SELECT 1".to_string(),
+    ));
+    let (status, message) = match tokio::time::timeout(DB_CHECK_TIMEOUT, query).await {
+        Ok(Ok(_)) => (HealthStatus::Ok, None),
+        Ok(Err(e)) => (HealthStatus::Fail, Some(e.to_string())),
+        Err(_) => (
+            HealthStatus::Fail,
+            Some(format!("Нет ответа за {} с", DB_CHECK_TIMEOUT.as_secs())),
+        ),
+    };
+    HealthCheckDto {
+        name: "database".to_string(),
+        status,
+        critical: true,
+        latency_ms: elapsed_ms(start),
+        message,
+    }
+}
+
+/// Состояние воркера: `interval_secs == 0` — не запущен, `last_tick == 0` — тактов не было.
+fn worker_status(
+    config_enabled: bool,
+    interval_secs: u64,
+    last_tick: i64,
+    now: i64,
+) -> (HealthStatus, Option<String>) {
+    if !config_enabled {
+        return (
+            HealthStatus::Disabled,
+            Some("Выключен в config.toml ([scheduled_tasks].enabled)".to_string()),
+        );
+    }
+    if interval_secs == 0 {
+        return (HealthStatus::Fail, Some("Воркер не запущен".to_string()));
+    }
+    if last_tick == 0 {
+        return (
+            HealthStatus::Fail,
+            Some("Воркер не выполнил ни одного такта".to_string()),
+        );
+    }
+    let age = now - last_tick;
+    if age > interval_secs as i64 * WORKER_MISSED_TICKS {
+        return (
+            HealthStatus::Fail,
+            Some(format!(
+                "Последний такт {} с назад при интервале {} с",
+                age, interval_secs
+            )),
+        );
+    }
+    (HealthStatus::Ok, None)
+}
+
+fn check_worker() -> HealthCheckDto {
+    let start = Instant::now();
+    let (status, message) = worker_status(
+        get_scheduler_config_enabled(),
+        WORKER_INTERVAL_SECS.load(Ordering::SeqCst),
+        WORKER_LAST_TICK.load(Ordering::SeqCst),
+        Utc::now().timestamp(),
+    );
+    HealthCheckDto {
+        name: "scheduled_task_worker".to_string(),
+        status,
+        critical: false,
+        latency_ms: elapsed_ms(start),
+        message,
+    }
+}
+
+fn check_startup() -> HealthCheckDto {
+    let complete = STARTUP_COMPLETE.load(Ordering::SeqCst);
+    HealthCheckDto {
+        name: "startup".to_string(),
+        status: if complete {
+            HealthStatus::Ok
+        } else {
+            HealthStatus::Fail
+        },
+        critical: true,
+        latency_ms: 0,
+        message: (!complete).then(|| "Инициализация сервера не завершена".to_string()),
+    }
+}
+
+/// Общий статус: `Fail`, если упала критичная проверка; `Degraded` — если некритичная.
+fn summarize(checks: &[HealthCheckDto]) -> (HealthStatus, bool) {
+    let failed = |critical: bool| {
+        checks
+            .iter()
+            .any(|c| c.critical == critical && c.status == HealthStatus::Fail)
+    };
+    if failed(true) {
+        (HealthStatus::Fail, false)
+    } else if failed(false) {
+        (HealthStatus::Degraded, true)
+    } else {
+        (HealthStatus::Ok, true)
+    }
+}
+
+/// Выполняет все проверки.
+pub async fn report() -> HealthReportDto {
+    let checks = vec![check_startup(), check_database().await, check_worker()];
+    let (status, ready) = summarize(&checks);
+    HealthReportDto {
+        status,
+        ready,
+        uptime_secs: if STARTUP_COMPLETE.load(Ordering::SeqCst) {
+            STARTED_AT.elapsed().as_secs()
+        } else {
+            0
+        },
+        checked_at: Utc::now().to_rfc3339(),
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(critical: bool, status: HealthStatus) -> HealthCheckDto {
+        HealthCheckDto {
+            name: "x".to_string(),
+            status,
+            critical,
+            latency_ms: 0,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn worker_status_detects_stale_and_disabled_worker() {
+        assert_eq!(worker_status(false, 0, 0, 1000).0, HealthStatus::Disabled);
+        assert_eq!(worker_status(true, 0, 0, 1000).0, HealthStatus::Fail);
+        assert_eq!(worker_status(true, 60, 0, 1000).0, HealthStatus::Fail);
+        assert_eq!(worker_status(true, 60, 900, 1000).0, HealthStatus::Ok);
+        assert_eq!(worker_status(true, 60, 800, 1000).0, HealthStatus::Fail);
+    }
+
+    #[test]
+    fn only_critical_failures_make_server_not_ready() {
+        let ok = [
+            check(true, HealthStatus::Ok),
+            check(false, HealthStatus::Disabled),
+        ];
+        assert_eq!(summarize(&ok), (HealthStatus::Ok, true));
+
+        let degraded = [
+            check(true, HealthStatus::Ok),
+            check(false, HealthStatus::Fail),
+        ];
+        assert_eq!(summarize(&degraded), (HealthStatus::Degraded, true));
+
+        let failed = [
+            check(true, HealthStatus::Fail),
+            check(false, HealthStatus::Ok),
+        ];
+        assert_eq!(summarize(&failed), (HealthStatus::Fail, false));
+    }
+}
@@ -0,0 +1,61 @@
+//! Происхождение импортированных данных: параметры запроса, sha256 ответа и число
+//! строк по каждому пакету, полученному от API маркетплейса (`sys_import_batches`).
+//!
+//! Запись не должна ломать импорт: ошибка сохранения только пишется в журнал.
+
+pub mod repository;
+
+use chrono::{SecondsFormat, Utc};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Пакет ответа API для записи в журнал происхождения.
+pub struct ImportBatch<'a> {
+    pub session_id: &'a str,
+    pub connection_id: &'a str,
+    pub aggregate_index: &'a str,
+    pub endpoint: &'a str,
+    pub request_params: serde_json::Value,
+    pub response_body: &'a str,
+    pub row_count: usize,
+}
+
+pub fn response_sha256(body: &str) -> String {
+    format!("{:x}", Sha256::digest(body.as_bytes()))
+}
+
+pub async fn record_batch(batch: ImportBatch<'_>) {
+    let model = repository::Model {
+        id: Uuid::new_v4().to_string(),
+        session_id: batch.session_id.to_string(),
+        connection_id: batch.connection_id.to_string(),
+        aggregate_index: batch.aggregate_index.to_string(),
+        endpoint: batch.endpoint.to_string(),
+        request_params: batch.request_params.to_string(),
+        response_sha256: response_sha256(batch.response_body),
+        response_bytes: batch.response_body.len() as i64,
+        row_count: batch.row_count as i64,
+        fetched_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+    };
+    if let Err(e) = repository::insert(model).await {
+        tracing::warn!(
+            "import provenance: failed to record {} batch for session {}: {}",
+            batch.aggregate_index,
+            batch.session_id,
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksum_is_sha256_hex() {
+        assert_eq!(
+            response_sha256("[]"),
+            "4f53cda18c2baa0c0354bb5f9a3ecbe5ed12ab4d8e11ba873c2f11161202b945"
+        );
+    }
+}
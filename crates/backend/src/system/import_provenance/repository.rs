@@ -0,0 +1,85 @@
+use contracts::system::import_provenance::ImportBatchDto;
+use sea_orm::entity::prelude::*;
+use sea_orm::{QueryOrder, Set};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sys_import_batches")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub session_id: String,
+    pub connection_id: String,
+    pub aggregate_index: String,
+    pub endpoint: String,
+    pub request_params: String,
+    pub response_sha256: String,
+    pub response_bytes: i64,
+    pub row_count: i64,
+    /// UTC RFC3339.
+    pub fetched_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+fn conn() -> &'static DatabaseConnection {
+    get_connection()
+}
+
+impl From<Model> for ImportBatchDto {
+    fn from(m: Model) -> Self {
+        Self {
+            id: m.id,
+            session_id: m.session_id,
+            connection_id: m.connection_id,
+            aggregate_index: m.aggregate_index,
+            endpoint: m.endpoint,
+            request_params: m.request_params,
+            response_sha256: m.response_sha256,
+            response_bytes: m.response_bytes,
+            row_count: m.row_count,
+            fetched_at: m.fetched_at,
+        }
+    }
+}
+
+pub async fn insert(m: Model) -> Result<(), DbErr> {
+    ActiveModel {
+        id: Set(m.id),
+        session_id: Set(m.session_id),
+        connection_id: Set(m.connection_id),
+        aggregate_index: Set(m.aggregate_index),
+        endpoint: Set(m.endpoint),
+        request_params: Set(m.request_params),
+        response_sha256: Set(m.response_sha256),
+        response_bytes: Set(m.response_bytes),
+        row_count: Set(m.row_count),
+        fetched_at: Set(m.fetched_at),
+    }
+    .insert(conn())
+    .await?;
+    Ok(())
+}
+
+/// Пакеты сессии в порядке получения; `aggregate_index` — только одного агрегата.
+pub async fn list_by_session(
+    session_id: &str,
+    aggregate_index: Option<&str>,
+) -> Result<Vec<ImportBatchDto>, DbErr> {
+    let mut query = Entity::find().filter(Column::SessionId.eq(session_id));
+    if let Some(aggregate_index) = aggregate_index {
+        query = query.filter(Column::AggregateIndex.eq(aggregate_index));
+    }
+    Ok(query
+        .order_by_asc(Column::FetchedAt)
+        .order_by_asc(Column::Id)
+        .all(conn())
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
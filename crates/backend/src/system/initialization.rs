@@ -18,6 +18,8 @@ pub async fn ensure_admin_user_exists() -> Result<()> {
             full_name: Some("Administrator".to_string()),
             is_admin: true,
             primary_role_code: "admin".to_string(),
+            active_from: None,
+            active_until: None,
         };
 
         let admin_id = service::create(admin_dto, None).await?;
@@ -0,0 +1,201 @@
+//! Разбор строк `tracing_subscriber::fmt` без ANSI:
+//! `2026-10-17T10:00:00.123456Z  INFO request{request_id=…}: backend::module: сообщение`.
+//! Строки без метки времени (многострочные сообщения, паники) дописываются к предыдущей записи.
+
+use contracts::system::log_viewer::{LogEntry, LOG_LEVELS};
+
+/// Ранг уровня: 0 — `ERROR`, 4 — `TRACE`; `None` для неизвестного.
+pub fn level_rank(level: &str) -> Option<usize> {
+    let level = level.trim();
+    LOG_LEVELS
+        .iter()
+        .position(|l| l.eq_ignore_ascii_case(level))
+}
+
+fn is_target(token: &str) -> bool {
+    !token.is_empty()
+        && token
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+}
+
+/// `request_id` из контекста span'ов (`request{request_id=abc}:task{…}`).
+fn span_request_id(spans: &str) -> Option<String> {
+    let start = spans.find("request_id=")? + "request_id=".len();
+    let value: String = spans[start..]
+        .chars()
+        .take_while(|c| !matches!(c, '}' | ' ' | ','))
+        .collect();
+    (!value.is_empty()).then_some(value)
+}
+
+/// Одна строка журнала; `None` — строка-продолжение предыдущей записи.
+pub fn parse_line(line: &str) -> Option<LogEntry> {
+    let (timestamp, rest) = line.split_once(' ')?;
+    if chrono::DateTime::parse_from_rfc3339(timestamp).is_err() {
+        return None;
+    }
+    let rest = rest.trim_start();
+    let (level, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+    level_rank(level)?;
+
+    // Контекст span'ов содержит `{`, target — только путь модуля
+    let mut rest = rest.trim_start();
+    let mut request_id = None;
+    if let Some((head, tail)) = rest.split_once(": ") {
+        if head.contains('{') {
+            request_id = span_request_id(head);
+            rest = tail;
+        }
+    }
+    let (target, message) = match rest.split_once(": ") {
+        Some((target, message)) if is_target(target) => (target, message),
+        _ => ("", rest),
+    };
+
+    Some(LogEntry {
+        timestamp: Some(timestamp.to_string()),
+        level: level.to_string(),
+        target: target.to_string(),
+        request_id,
+        message: message.to_string(),
+    })
+}
+
+/// Разбор текста журнала в записи (в порядке файла).
+pub fn parse_log(text: &str) -> Vec<LogEntry> {
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for line in text.lines().filter(|l| !l.trim().is_empty()) {
+        match parse_line(line) {
+            Some(entry) => entries.push(entry),
+            None => match entries.last_mut() {
+                Some(last) => {
+                    last.message.push('\n');
+                    last.message.push_str(line);
+                }
+                None => entries.push(LogEntry {
+                    timestamp: None,
+                    level: "INFO".to_string(),
+                    target: String::new(),
+                    request_id: None,
+                    message: line.to_string(),
+                }),
+            },
+        }
+    }
+    entries
+}
+
+/// Подготовленные фильтры (нижний регистр, ранг уровня).
+#[derive(Debug, Default)]
+pub struct LogFilter {
+    pub max_rank: Option<usize>,
+    pub module: Option<String>,
+    pub text: Option<String>,
+    pub request_id: Option<String>,
+}
+
+fn non_empty_lower(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_lowercase)
+}
+
+impl LogFilter {
+    pub fn new(
+        level: Option<&str>,
+        module: Option<&str>,
+        text: Option<&str>,
+        request_id: Option<&str>,
+    ) -> Self {
+        Self {
+            max_rank: level.and_then(level_rank),
+            module: non_empty_lower(module),
+            text: non_empty_lower(text),
+            request_id: non_empty_lower(request_id),
+        }
+    }
+
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if let Some(max_rank) = self.max_rank {
+            if level_rank(&entry.level).is_some_and(|rank| rank > max_rank) {
+                return false;
+            }
+        }
+        if let Some(module) = &self.module {
+            if !entry.target.to_lowercase().contains(module) {
+                return false;
+            }
+        }
+        if let Some(text) = &self.text {
+            if !entry.message.to_lowercase().contains(text) {
+                return false;
+            }
+        }
+        if let Some(request_id) = &self.request_id {
+            // Id мог попасть и в текст (фоновые задачи логируют его явно)
+            let in_span = entry
+                .request_id
+                .as_deref()
+                .is_some_and(|id| id.to_lowercase() == *request_id);
+            if !in_span && !entry.message.to_lowercase().contains(request_id) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_and_span_lines() {
+        let entry = parse_line(
+            "2026-10-17T10:00:00.123456Z  INFO backend::system::tasks::worker: Task 'x' is due",
+        )
+        .unwrap();
+        assert_eq!(entry.level, "INFO");
+        assert_eq!(entry.target, "backend::system::tasks::worker");
+        assert_eq!(entry.message, "Task 'x' is due");
+        assert_eq!(entry.request_id, None);
+
+        let entry = parse_line(
+            "2026-10-17T10:00:01.000001Z ERROR request{request_id=5f0c-11}: backend::api: failed: timeout",
+        )
+        .unwrap();
+        assert_eq!(entry.level, "ERROR");
+        assert_eq!(entry.request_id.as_deref(), Some("5f0c-11"));
+        assert_eq!(entry.target, "backend::api");
+        assert_eq!(entry.message, "failed: timeout");
+    }
+
+    #[test]
+    fn continuation_lines_join_previous_entry() {
+        let text = "2026-10-17T10:00:00Z  WARN backend::a: first\n  second line\n\
+                    2026-10-17T10:00:02Z  INFO backend::b: next\n";
+        let entries = parse_log(text);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message, "first\n  second line");
+        assert_eq!(entries[1].target, "backend::b");
+    }
+
+    #[test]
+    fn filter_by_level_module_text_and_request_id() {
+        let entries = parse_log(
+            "2026-10-17T10:00:00Z ERROR request{request_id=abc}: backend::system::tasks: Boom\n\
+             2026-10-17T10:00:01Z  INFO backend::system::tasks: fine\n\
+             2026-10-17T10:00:02Z DEBUG backend::api: verbose abc\n",
+        );
+        let count = |filter: LogFilter| entries.iter().filter(|e| filter.matches(e)).count();
+
+        assert_eq!(count(LogFilter::new(Some("warn"), None, None, None)), 1);
+        assert_eq!(count(LogFilter::new(Some("INFO"), None, None, None)), 2);
+        assert_eq!(count(LogFilter::new(None, Some("Tasks"), None, None)), 2);
+        assert_eq!(count(LogFilter::new(None, None, Some("boom"), None)), 1);
+        assert_eq!(count(LogFilter::new(None, None, None, Some("ABC"))), 2);
+        assert_eq!(count(LogFilter::new(None, None, None, Some(" "))), 3);
+    }
+}
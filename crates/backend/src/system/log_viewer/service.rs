@@ -0,0 +1,61 @@
+use anyhow::{anyhow, Result};
+use contracts::system::log_viewer::{LogQuery, LogQueryResponse};
+use std::io::{Read, Seek, SeekFrom};
+
+use super::parser::{parse_log, LogFilter};
+
+/// Сколько байт с конца файла журнала просматривается за запрос.
+const TAIL_BYTES: u64 = 16 * 1024 * 1024;
+const DEFAULT_LIMIT: usize = 500;
+const MAX_LIMIT: usize = 5000;
+
+/// Хвост файла; первая (обрезанная) строка отбрасывается, если читаем не с начала.
+fn read_tail(path: &std::path::Path) -> Result<(String, u64)> {
+    let mut file = std::fs::File::open(path)?;
+    let size = file.metadata()?.len();
+    let offset = size.saturating_sub(TAIL_BYTES);
+    file.seek(SeekFrom::Start(offset))?;
+    let mut bytes = Vec::with_capacity((size - offset) as usize);
+    file.read_to_end(&mut bytes)?;
+    let mut text = String::from_utf8_lossy(&bytes).into_owned();
+    if offset > 0 {
+        let first_newline = text.find('\n').map_or(text.len(), |i| i + 1);
+        text.drain(..first_newline);
+    }
+    Ok((text, size))
+}
+
+/// Последние записи журнала, подходящие под фильтры (новые первыми).
+pub async fn query(query: LogQuery) -> Result<LogQueryResponse> {
+    let path = crate::system::tracing::log_file_path()
+        .ok_or_else(|| anyhow!("Log file is not configured"))?
+        .to_path_buf();
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    tokio::task::spawn_blocking(move || {
+        let (text, file_size) = read_tail(&path)?;
+        let entries = parse_log(&text);
+        let filter = LogFilter::new(
+            query.level.as_deref(),
+            query.module.as_deref(),
+            query.q.as_deref(),
+            query.request_id.as_deref(),
+        );
+        let mut matched: Vec<_> = entries
+            .iter()
+            .rev()
+            .filter(|entry| filter.matches(entry))
+            .take(limit + 1)
+            .cloned()
+            .collect();
+        let truncated = matched.len() > limit;
+        matched.truncate(limit);
+        Ok(LogQueryResponse {
+            entries: matched,
+            scanned: entries.len(),
+            truncated,
+            file_size,
+        })
+    })
+    .await?
+}
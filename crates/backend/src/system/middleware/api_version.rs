@@ -0,0 +1,206 @@
+//! Версионирование публичного REST API.
+//!
+//! Канонические пути — `/api/v1/...`: middleware переписывает их в `/api/...` до
+//! маршрутизации, поэтому обработчики и таблица маршрутов остаются прежними.
+//! Внешние интеграции (вебхуки, BI-токены) привязываются к версии, а внутренние
+//! эндпоинты можно менять, не ломая их.
+//!
+//! Старые пути без версии продолжают работать, но в ответ добавляются заголовки
+//! `Deprecation: true` и `Link: </api/v1/...>; rel="successor-version"`. Клиент,
+//! который не может сменить URL, выбирает версию заголовком `Api-Version: 1` —
+//! тогда ответ без пометки устаревания. Неподдерживаемая версия (в пути или в
+//! заголовке) отклоняется ошибкой `AppError` до вызова обработчика.
+
+use axum::body::Body;
+use axum::http::uri::PathAndQuery;
+use axum::http::{HeaderValue, Request, Uri};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+
+use crate::shared::error::AppError;
+
+/// Заголовок согласования версии (в запросе) и фактической версии (в ответе).
+pub const API_VERSION_HEADER: &str = "api-version";
+
+/// Текущая версия публичного API.
+pub const CURRENT_VERSION: u32 = 1;
+
+/// Версии, которые принимает сервер.
+pub const SUPPORTED_VERSIONS: &[u32] = &[1];
+
+/// Оборачивает собранное приложение внешним роутером: middleware внешнего роутера
+/// выполняется до маршрутизации внутреннего, поэтому переписанный путь находит
+/// существующие маршруты `/api/...`.
+pub fn with_api_versioning(app: Router) -> Router {
+    Router::new()
+        .fallback_service(app)
+        .layer(middleware::from_fn(api_version))
+}
+
+/// Разбор пути запроса относительно версий API.
+#[derive(Debug, PartialEq, Eq)]
+enum ApiPath<'a> {
+    /// `/api/v{N}/rest` — версия и остаток пути (`/rest` или пустой).
+    Versioned { version: &'a str, rest: &'a str },
+    /// `/api/...` без сегмента версии.
+    Unversioned,
+    /// Не API (статика фронтенда).
+    Other,
+}
+
+fn classify(path: &str) -> ApiPath<'_> {
+    let Some(tail) = path.strip_prefix("/api") else {
+        return ApiPath::Other;
+    };
+    if !(tail.is_empty() || tail.starts_with('/')) {
+        return ApiPath::Other;
+    }
+    let Some(segment_tail) = tail.strip_prefix("/v") else {
+        return ApiPath::Unversioned;
+    };
+    let end = segment_tail.find('/').unwrap_or(segment_tail.len());
+    let (version, rest) = segment_tail.split_at(end);
+    if version.is_empty() || !version.chars().all(|c| c.is_ascii_digit()) {
+        return ApiPath::Unversioned;
+    }
+    ApiPath::Versioned { version, rest }
+}
+
+fn parse_supported(version: &str) -> Option<u32> {
+    version
+        .trim()
+        .trim_start_matches(['v', 'V'])
+        .parse::<u32>()
+        .ok()
+        .filter(|v| SUPPORTED_VERSIONS.contains(v))
+}
+
+fn unsupported(version: &str) -> Response {
+    let supported = SUPPORTED_VERSIONS
+        .iter()
+        .map(|v| format!("v{v}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    AppError::bad_request(format!(
+        "Версия API '{}' не поддерживается; поддерживаемые: {}",
+        version.trim(),
+        supported
+    ))
+    .into_response()
+}
+
+fn set_version_header(response: &mut Response, version: u32) {
+    response
+        .headers_mut()
+        .insert(API_VERSION_HEADER, HeaderValue::from(version));
+}
+
+/// Заменяет путь запроса, сохраняя query string.
+fn rewrite_path(req: &mut Request<Body>, path: &str) -> Result<(), AppError> {
+    let path_and_query = match req.uri().query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    };
+    let mut parts = req.uri().clone().into_parts();
+    parts.path_and_query = Some(
+        PathAndQuery::try_from(path_and_query).map_err(|e| AppError::bad_request(e.to_string()))?,
+    );
+    *req.uri_mut() = Uri::from_parts(parts).map_err(|e| AppError::bad_request(e.to_string()))?;
+    Ok(())
+}
+
+pub async fn api_version(mut req: Request<Body>, next: Next) -> Response {
+    let path = req.uri().path().to_string();
+    match classify(&path) {
+        ApiPath::Other => next.run(req).await,
+        ApiPath::Versioned { version, rest } => {
+            let Some(version) = parse_supported(version) else {
+                return unsupported(&format!("v{version}"));
+            };
+            if let Err(error) = rewrite_path(&mut req, &format!("/api{rest}")) {
+                return error.into_response();
+            }
+            let mut response = next.run(req).await;
+            set_version_header(&mut response, version);
+            response
+        }
+        ApiPath::Unversioned => {
+            let requested = req
+                .headers()
+                .get(API_VERSION_HEADER)
+                .map(|value| value.to_str().unwrap_or_default().to_string());
+            match requested {
+                Some(requested) => {
+                    let Some(version) = parse_supported(&requested) else {
+                        return unsupported(&requested);
+                    };
+                    let mut response = next.run(req).await;
+                    set_version_header(&mut response, version);
+                    response
+                }
+                None => {
+                    let successor = successor_link(&path);
+                    let mut response = next.run(req).await;
+                    set_version_header(&mut response, CURRENT_VERSION);
+                    let headers = response.headers_mut();
+                    headers.insert("deprecation", HeaderValue::from_static("true"));
+                    if let Ok(link) = HeaderValue::from_str(&successor) {
+                        headers.insert(axum::http::header::LINK, link);
+                    }
+                    response
+                }
+            }
+        }
+    }
+}
+
+/// `Link` на тот же ресурс в текущей версии API.
+fn successor_link(path: &str) -> String {
+    let rest = path.strip_prefix("/api").unwrap_or(path);
+    format!("</api/v{CURRENT_VERSION}{rest}>; rel=\"successor-version\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_versioned_and_legacy_paths() {
+        assert_eq!(
+            classify("/api/v1/a015/wb-orders/list"),
+            ApiPath::Versioned {
+                version: "1",
+                rest: "/a015/wb-orders/list"
+            }
+        );
+        assert_eq!(
+            classify("/api/v1"),
+            ApiPath::Versioned {
+                version: "1",
+                rest: ""
+            }
+        );
+        assert_eq!(classify("/api/a015/wb-orders/list"), ApiPath::Unversioned);
+        // Сегмент, начинающийся с `v`, но не номер версии — обычный путь.
+        assert_eq!(classify("/api/vendors"), ApiPath::Unversioned);
+        assert_eq!(classify("/apidocs"), ApiPath::Other);
+        assert_eq!(classify("/index.html"), ApiPath::Other);
+    }
+
+    #[test]
+    fn negotiates_only_supported_versions() {
+        assert_eq!(parse_supported("1"), Some(1));
+        assert_eq!(parse_supported(" v1 "), Some(1));
+        assert_eq!(parse_supported("2"), None);
+        assert_eq!(parse_supported("latest"), None);
+    }
+
+    #[test]
+    fn successor_link_points_to_current_version() {
+        assert_eq!(
+            successor_link("/api/p904/sales-data"),
+            "</api/v1/p904/sales-data>; rel=\"successor-version\""
+        );
+    }
+}
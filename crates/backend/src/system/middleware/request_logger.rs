@@ -1,14 +1,44 @@
 use axum::body::to_bytes;
 use axum::body::Body;
-use axum::http::Request;
+use axum::http::{HeaderValue, Request};
 use axum::middleware::Next;
 use axum::response::Response;
 use chrono::Utc;
+use tracing::Instrument;
+use uuid::Uuid;
 
 use crate::shared::format::format_number;
 
+/// Заголовок correlation id: принимается от клиента/прокси или генерируется.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Correlation id текущего запроса (внутри обработчика); `None` вне запроса
+/// и в задачах, запущенных через `tokio::spawn`.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Correlation id из заголовка запроса, если он похож на идентификатор.
+fn incoming_request_id(req: &Request<Body>) -> Option<String> {
+    let value = req.headers().get(REQUEST_ID_HEADER)?.to_str().ok()?.trim();
+    let valid = !value.is_empty()
+        && value.len() <= 64
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then(|| value.to_string())
+}
+
 /// Middleware для логирования HTTP запросов
 ///
+/// Обработчик выполняется в span `request{request_id=…}`: все записи журнала запроса
+/// несут correlation id (поиск в «Журнале сервера»), он же возвращается в `x-request-id`
+/// и попадает в тело ошибок `AppError` (см. [`current_request_id`]).
+///
 /// Выводит в консоль:
 /// - Timestamp (MSK, UTC+3)
 /// - Длительность (ms)
@@ -19,9 +49,16 @@ pub async fn request_logger(req: Request<Body>, next: Next) -> Response {
     let start = std::time::Instant::now();
     let method = req.method().clone();
     let uri = req.uri().clone();
+    let request_id = incoming_request_id(&req).unwrap_or_else(|| Uuid::new_v4().to_string());
 
-    let response = next.run(req).await;
-    let (parts, body) = response.into_parts();
+    let span = tracing::info_span!("request", request_id = %request_id);
+    let response = REQUEST_ID
+        .scope(request_id.clone(), next.run(req).instrument(span))
+        .await;
+    let (mut parts, body) = response.into_parts();
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        parts.headers.insert(REQUEST_ID_HEADER, value);
+    }
 
     // Читаем тело ответа, чтобы узнать реальный размер
     let bytes = match to_bytes(body, usize::MAX).await {
@@ -1,14 +1,38 @@
 pub mod access;
+pub mod announcements;
 pub mod api;
 pub mod audit;
 pub mod auth;
+pub mod branding;
+pub mod bulk_ops;
+pub mod config_bundle;
+pub mod data_freshness;
+pub mod description_templates;
+pub mod document_audit;
+pub mod environment;
+pub mod cdc;
+pub mod export_profiles;
+pub mod exports;
+pub mod external_refs;
 pub mod ext_api_log;
 pub mod favorites;
+pub mod health;
 pub mod history;
+pub mod import_provenance;
 pub mod initialization;
+pub mod log_viewer;
 pub mod middleware;
+pub mod notifications;
+pub mod operations;
+pub mod org_context;
+pub mod presence;
+pub mod print_templates;
+pub mod quotas;
+pub mod return_dispositions;
 pub mod roles;
 pub mod s3;
+pub mod scheduled_posts;
+pub mod schema_drift;
 pub mod settings;
 pub mod tasks;
 pub mod tracing;
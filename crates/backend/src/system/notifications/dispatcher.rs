@@ -0,0 +1,142 @@
+//! Диспетчер уведомлений: источник события передаёт кандидатов (например, всех
+//! администраторов), а диспетчер доставляет уведомление каждому только по тем каналам,
+//! которые пользователь включил в своей матрице настроек.
+//!
+//! Ошибки доставки не прерывают рассылку: они логируются и попадают в отчёт.
+
+use contracts::system::notifications::{
+    NotificationChannel, NotificationEvent, NotificationPreferencesDto,
+};
+use contracts::system::users::User;
+
+use super::{repository, service};
+use crate::shared::config::get_mail_config;
+use crate::shared::{mail, telegram};
+use crate::system::users::repository as user_repository;
+
+/// Уведомление для рассылки.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub event: NotificationEvent,
+    pub title: String,
+    pub body: String,
+    /// Вкладка приложения, которую открыть по клику во входящих
+    pub tab_key: Option<String>,
+    /// Ссылка на приложение для писем и Telegram
+    pub link: Option<String>,
+}
+
+impl Notification {
+    fn external_text(&self) -> String {
+        match &self.link {
+            Some(link) => format!("{}\n\n{}", self.body, link),
+            None => self.body.clone(),
+        }
+    }
+}
+
+/// Итог рассылки по каналам.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DispatchReport {
+    pub in_app: usize,
+    pub email: usize,
+    pub telegram: usize,
+    pub failed: usize,
+}
+
+/// Id активных пользователей; `admins_only` — только администраторы.
+pub async fn active_user_ids(admins_only: bool) -> anyhow::Result<Vec<String>> {
+    Ok(user_repository::list_all()
+        .await?
+        .into_iter()
+        .filter(|u| u.is_active && (!admins_only || u.is_admin))
+        .map(|u| u.id)
+        .collect())
+}
+
+async fn deliver(
+    user: &User,
+    prefs: &NotificationPreferencesDto,
+    notification: &Notification,
+    report: &mut DispatchReport,
+) {
+    let event = notification.event;
+    let code = event.code();
+
+    if prefs.is_enabled(event, NotificationChannel::InApp) {
+        match repository::insert_notification(
+            &user.id,
+            event,
+            &notification.title,
+            &notification.body,
+            notification.tab_key.as_deref(),
+        )
+        .await
+        {
+            Ok(()) => report.in_app += 1,
+            Err(e) => {
+                report.failed += 1;
+                tracing::warn!("[notify:{code}] in-app for {} failed: {e}", user.username);
+            }
+        }
+    }
+
+    if prefs.is_enabled(event, NotificationChannel::Email) && get_mail_config().enabled {
+        if let Some(email) = prefs.email.as_deref() {
+            let sent = match mail::check_and_record_send() {
+                Ok(()) => {
+                    mail::send_email(email, &notification.title, &notification.external_text())
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            match sent {
+                Ok(()) => report.email += 1,
+                Err(e) => {
+                    report.failed += 1;
+                    tracing::warn!("[notify:{code}] email to {email} failed: {e}");
+                }
+            }
+        }
+    }
+
+    if prefs.is_enabled(event, NotificationChannel::Telegram) && prefs.telegram_available {
+        if let Some(chat_id) = prefs.telegram_chat_id.as_deref() {
+            let text = format!("{}\n\n{}", notification.title, notification.external_text());
+            match telegram::send_message(chat_id, &text).await {
+                Ok(()) => report.telegram += 1,
+                Err(e) => {
+                    report.failed += 1;
+                    tracing::warn!("[notify:{code}] telegram for {} failed: {e}", user.username);
+                }
+            }
+        }
+    }
+}
+
+/// Рассылает уведомление кандидатам по их настройкам.
+pub async fn dispatch(notification: &Notification, user_ids: &[String]) -> DispatchReport {
+    let mut report = DispatchReport::default();
+    for user_id in user_ids {
+        let user = match user_repository::get_by_id(user_id).await {
+            Ok(Some(user)) if user.is_active => user,
+            Ok(_) => continue,
+            Err(e) => {
+                report.failed += 1;
+                tracing::warn!("[notify] failed to load user {user_id}: {e}");
+                continue;
+            }
+        };
+        match service::preferences_for(user_id).await {
+            Ok(prefs) => deliver(&user, &prefs, notification, &mut report).await,
+            Err(e) => {
+                report.failed += 1;
+                tracing::warn!(
+                    "[notify] failed to load preferences for {}: {e}",
+                    user.username
+                );
+            }
+        }
+    }
+    report
+}
@@ -0,0 +1,171 @@
+use anyhow::Result;
+use chrono::Utc;
+use contracts::system::notifications::{
+    NotificationChannel, NotificationDto, NotificationEvent, NotificationPreference,
+};
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement, TransactionTrait};
+
+use crate::shared::data::db::get_connection;
+
+/// Сохранённые ячейки матрицы пользователя (неизвестные коды пропускаются).
+pub async fn list_preferences(user_id: &str) -> Result<Vec<NotificationPreference>> {
+    let rows = get_connection()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT event, channel, enabled FROM sys_notification_preferences WHERE user_id = ?",
+            [user_id.into()],
+        ))
+        .await?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let event: String = row.try_get("", "event").ok()?;
+            let channel: String = row.try_get("", "channel").ok()?;
+            let enabled: i32 = row.try_get("", "enabled").ok()?;
+            Some(NotificationPreference {
+                event: NotificationEvent::from_code(&event)?,
+                channel: NotificationChannel::from_code(&channel)?,
+                enabled: enabled != 0,
+            })
+        })
+        .collect())
+}
+
+pub async fn replace_preferences(
+    user_id: &str,
+    preferences: &[NotificationPreference],
+) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    let txn = get_connection().begin().await?;
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM sys_notification_preferences WHERE user_id = ?",
+        [user_id.into()],
+    ))
+    .await?;
+    for pref in preferences {
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO sys_notification_preferences (user_id, event, channel, enabled, updated_at)
+             VALUES (?, ?, ?, ?, ?)",
+            [
+                user_id.into(),
+                pref.event.code().into(),
+                pref.channel.code().into(),
+                (if pref.enabled { 1 } else { 0 }).into(),
+                now.clone().into(),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+pub async fn get_telegram_chat_id(user_id: &str) -> Result<Option<String>> {
+    let row = get_connection()
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT telegram_chat_id FROM sys_notification_contacts WHERE user_id = ?",
+            [user_id.into()],
+        ))
+        .await?;
+    Ok(row.and_then(|r| {
+        r.try_get::<Option<String>>("", "telegram_chat_id")
+            .ok()
+            .flatten()
+    }))
+}
+
+pub async fn set_telegram_chat_id(user_id: &str, chat_id: Option<&str>) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    get_connection()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO sys_notification_contacts (user_id, telegram_chat_id, updated_at) VALUES (?, ?, ?)
+             ON CONFLICT(user_id) DO UPDATE SET telegram_chat_id = excluded.telegram_chat_id, updated_at = excluded.updated_at",
+            [
+                user_id.into(),
+                chat_id.map(str::to_string).into(),
+                now.into(),
+            ],
+        ))
+        .await?;
+    Ok(())
+}
+
+pub async fn insert_notification(
+    user_id: &str,
+    event: NotificationEvent,
+    title: &str,
+    body: &str,
+    tab_key: Option<&str>,
+) -> Result<()> {
+    get_connection()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO sys_notifications (id, user_id, event, title, body, tab_key, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            [
+                uuid::Uuid::new_v4().to_string().into(),
+                user_id.into(),
+                event.code().into(),
+                title.into(),
+                body.into(),
+                tab_key.map(str::to_string).into(),
+                Utc::now().to_rfc3339().into(),
+            ],
+        ))
+        .await?;
+    Ok(())
+}
+
+pub async fn list_notifications(user_id: &str, limit: u64) -> Result<Vec<NotificationDto>> {
+    let rows = get_connection()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT id, event, title, body, tab_key, created_at, read_at FROM sys_notifications
+             WHERE user_id = ? ORDER BY created_at DESC LIMIT ?",
+            [user_id.into(), (limit as i64).into()],
+        ))
+        .await?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let event: String = row.try_get("", "event").ok()?;
+            Some(NotificationDto {
+                id: row.try_get("", "id").ok()?,
+                event: NotificationEvent::from_code(&event)?,
+                title: row.try_get("", "title").ok()?,
+                body: row.try_get("", "body").ok()?,
+                tab_key: row.try_get("", "tab_key").ok()?,
+                created_at: row.try_get("", "created_at").ok()?,
+                read_at: row.try_get("", "read_at").ok()?,
+            })
+        })
+        .collect())
+}
+
+pub async fn count_unread(user_id: &str) -> Result<i64> {
+    let row = get_connection()
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT COUNT(*) AS cnt FROM sys_notifications WHERE user_id = ? AND read_at IS NULL",
+            [user_id.into()],
+        ))
+        .await?;
+    Ok(row
+        .and_then(|r| r.try_get::<i64>("", "cnt").ok())
+        .unwrap_or(0))
+}
+
+pub async fn mark_all_read(user_id: &str) -> Result<()> {
+    get_connection()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_notifications SET read_at = ? WHERE user_id = ? AND read_at IS NULL",
+            [Utc::now().to_rfc3339().into(), user_id.into()],
+        ))
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use anyhow::{anyhow, Result};
+use contracts::system::notifications::{
+    NotificationChannel, NotificationEvent, NotificationInboxDto, NotificationPreference,
+    NotificationPreferencesDto, NotificationPreferencesUpdate,
+};
+
+use super::repository;
+use crate::shared::config::{get_mail_config, get_telegram_config};
+use crate::system::users::repository as user_repository;
+
+/// Сколько последних уведомлений показывает колокольчик
+const INBOX_LIMIT: u64 = 50;
+
+/// Полная матрица событие × канал: сохранённые ячейки, остальные — по умолчанию.
+fn full_matrix(stored: &[NotificationPreference]) -> Vec<NotificationPreference> {
+    NotificationEvent::ALL
+        .into_iter()
+        .flat_map(|event| {
+            NotificationChannel::ALL.into_iter().map(move |channel| {
+                let enabled = stored
+                    .iter()
+                    .find(|p| p.event == event && p.channel == channel)
+                    .map_or_else(|| channel.enabled_by_default(), |p| p.enabled);
+                NotificationPreference {
+                    event,
+                    channel,
+                    enabled,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Chat id Telegram: число (в т.ч. отрицательное для групп) или `@username` канала.
+fn normalize_chat_id(value: Option<String>) -> Result<Option<String>> {
+    let Some(value) = value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    else {
+        return Ok(None);
+    };
+    let digits = value.strip_prefix('-').unwrap_or(&value);
+    let numeric = !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit());
+    let channel = value.len() > 1
+        && value.starts_with('@')
+        && value[1..]
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if numeric || channel {
+        Ok(Some(value))
+    } else {
+        Err(anyhow!("Invalid Telegram chat id"))
+    }
+}
+
+pub async fn preferences_for(user_id: &str) -> Result<NotificationPreferencesDto> {
+    let user = user_repository::get_by_id(user_id)
+        .await?
+        .ok_or_else(|| anyhow!("User not found"))?;
+    let stored = repository::list_preferences(user_id).await?;
+    Ok(NotificationPreferencesDto {
+        preferences: full_matrix(&stored),
+        email: user.email.filter(|e| !e.trim().is_empty()),
+        telegram_chat_id: repository::get_telegram_chat_id(user_id).await?,
+        email_available: get_mail_config().enabled,
+        telegram_available: get_telegram_config().is_ready(),
+    })
+}
+
+pub async fn save_preferences(
+    user_id: &str,
+    update: NotificationPreferencesUpdate,
+) -> Result<NotificationPreferencesDto> {
+    let chat_id = normalize_chat_id(update.telegram_chat_id)?;
+    repository::replace_preferences(user_id, &full_matrix(&update.preferences)).await?;
+    repository::set_telegram_chat_id(user_id, chat_id.as_deref()).await?;
+    preferences_for(user_id).await
+}
+
+pub async fn inbox(user_id: &str) -> Result<NotificationInboxDto> {
+    Ok(NotificationInboxDto {
+        items: repository::list_notifications(user_id, INBOX_LIMIT).await?,
+        unread: repository::count_unread(user_id).await?,
+    })
+}
+
+pub async fn mark_all_read(user_id: &str) -> Result<()> {
+    repository::mark_all_read(user_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matrix_fills_defaults_for_missing_cells() {
+        let stored = [
+            NotificationPreference {
+                event: NotificationEvent::AlertFired,
+                channel: NotificationChannel::InApp,
+                enabled: false,
+            },
+            NotificationPreference {
+                event: NotificationEvent::ImportFailed,
+                channel: NotificationChannel::Telegram,
+                enabled: true,
+            },
+        ];
+        let matrix = full_matrix(&stored);
+        assert_eq!(
+            matrix.len(),
+            NotificationEvent::ALL.len() * NotificationChannel::ALL.len()
+        );
+        let enabled = |event, channel| {
+            matrix
+                .iter()
+                .find(|p| p.event == event && p.channel == channel)
+                .unwrap()
+                .enabled
+        };
+        assert!(!enabled(
+            NotificationEvent::AlertFired,
+            NotificationChannel::InApp
+        ));
+        assert!(enabled(
+            NotificationEvent::ImportFailed,
+            NotificationChannel::Telegram
+        ));
+        assert!(enabled(
+            NotificationEvent::DocumentAssigned,
+            NotificationChannel::InApp
+        ));
+        assert!(!enabled(
+            NotificationEvent::DocumentAssigned,
+            NotificationChannel::Email
+        ));
+    }
+
+    #[test]
+    fn validates_telegram_chat_id() {
+        assert_eq!(
+            normalize_chat_id(Some(" 123456 ".to_string())).unwrap(),
+            Some("123456".to_string())
+        );
+        assert!(normalize_chat_id(Some("-100200".to_string())).is_ok());
+        assert!(normalize_chat_id(Some("@corp_alerts".to_string())).is_ok());
+        assert_eq!(normalize_chat_id(Some("  ".to_string())).unwrap(), None);
+        assert!(normalize_chat_id(Some("12ab".to_string())).is_err());
+        assert!(normalize_chat_id(Some("@".to_string())).is_err());
+        assert!(normalize_chat_id(Some("-".to_string())).is_err());
+    }
+}
@@ -0,0 +1,9 @@
+//! Пакетные операции (`/api/operations`).
+//!
+//! Массовые действия над документами не выполняются внутри HTTP-запроса: хендлер
+//! записывает операцию в `sys_operations` и возвращает её id, а исполнитель
+//! ([`service`]) обрабатывает элементы в фоне и пишет прогресс и итог. Клиент опрашивает
+//! `GET /api/operations/{id}`; список своих операций видит в шапке («Операции»).
+
+pub mod repository;
+pub mod service;
@@ -0,0 +1,141 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    Statement,
+};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sys_operations")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub user_id: String,
+    pub kind: String,
+    pub title: String,
+    pub request_json: String,
+    pub status: String,
+    pub processed: i64,
+    pub total: i64,
+    pub result_json: Option<String>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+fn conn() -> &'static DatabaseConnection {
+    get_connection()
+}
+
+pub async fn insert(model: Model) -> Result<(), DbErr> {
+    ActiveModel {
+        id: Set(model.id),
+        user_id: Set(model.user_id),
+        kind: Set(model.kind),
+        title: Set(model.title),
+        request_json: Set(model.request_json),
+        status: Set(model.status),
+        processed: Set(model.processed),
+        total: Set(model.total),
+        result_json: Set(model.result_json),
+        error: Set(model.error),
+        created_at: Set(model.created_at),
+        started_at: Set(model.started_at),
+        finished_at: Set(model.finished_at),
+    }
+    .insert(conn())
+    .await?;
+    Ok(())
+}
+
+pub async fn list_for_user(user_id: &str, limit: u64) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .order_by_desc(Column::CreatedAt)
+        .limit(limit)
+        .all(conn())
+        .await
+}
+
+pub async fn get_by_id(id: &str) -> Result<Option<Model>, DbErr> {
+    Entity::find_by_id(id.to_string()).one(conn()).await
+}
+
+pub async fn set_running(id: &str, status: &str, started_at: &str) -> Result<(), DbErr> {
+    conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_operations SET status = ?1, started_at = ?2 WHERE id = ?3",
+            vec![status.into(), started_at.into(), id.into()],
+        ))
+        .await?;
+    Ok(())
+}
+
+pub async fn set_progress(id: &str, processed: i64) -> Result<(), DbErr> {
+    conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_operations SET processed = ?1 WHERE id = ?2",
+            vec![processed.into(), id.into()],
+        ))
+        .await?;
+    Ok(())
+}
+
+pub async fn finish(
+    id: &str,
+    status: &str,
+    processed: i64,
+    result_json: Option<String>,
+    error: Option<String>,
+    finished_at: &str,
+) -> Result<(), DbErr> {
+    conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_operations \
+             SET status = ?1, processed = ?2, result_json = ?3, error = ?4, finished_at = ?5 \
+             WHERE id = ?6",
+            vec![
+                status.into(),
+                processed.into(),
+                result_json.into(),
+                error.into(),
+                finished_at.into(),
+                id.into(),
+            ],
+        ))
+        .await?;
+    Ok(())
+}
+
+/// Незавершённые операции предыдущего процесса сервера → `failed`.
+pub async fn fail_unfinished(
+    unfinished: &[&str],
+    status: &str,
+    error: &str,
+    finished_at: &str,
+) -> Result<u64, DbErr> {
+    let placeholders = vec!["?"; unfinished.len()].join(", ");
+    let mut values: Vec<sea_orm::Value> = vec![status.into(), error.into(), finished_at.into()];
+    values.extend(unfinished.iter().map(|s| (*s).into()));
+    let result = conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            &format!(
+                "UPDATE sys_operations SET status = ?, error = ?, finished_at = ? \
+                 WHERE status IN ({placeholders})"
+            ),
+            values,
+        ))
+        .await?;
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,415 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+use chrono::Utc;
+use contracts::system::auth::TokenClaims;
+use contracts::system::bulk_ops::BulkActionResultDto;
+use contracts::system::operations::{
+    OperationDto, OperationListResponse, OperationRequest, OperationStatus, OPERATION_MAX_ITEMS,
+};
+use once_cell::sync::Lazy;
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+
+use super::repository;
+use crate::system::bulk_ops;
+use crate::system::document_audit;
+
+/// Одновременно выполняемых операций; остальные ждут в статусе «В очереди».
+const MAX_CONCURRENT_OPERATIONS: usize = 2;
+const LIST_LIMIT: u64 = 50;
+/// Как часто сбрасывать прогресс в БД: опрос клиента чаще всё равно не увидит разницы.
+const PROGRESS_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+/// Сколько текстов ошибок по элементам сохранять в итоге.
+const MAX_ERRORS: usize = 20;
+
+/// Агрегаты, документы которых проводятся и распроводятся операциями
+/// `post_documents` / `unpost_documents`; id агрегата совпадает с его scope.
+const DOCUMENT_TYPES: &[(&str, &str)] = &[
+    ("a012_wb_sales", "Продажи WB"),
+    ("a013_ym_order", "Заказы Яндекс Маркета"),
+    ("a016_ym_returns", "Возвраты Яндекс Маркета"),
+];
+
+static OPERATION_SLOTS: Lazy<Semaphore> = Lazy::new(|| Semaphore::new(MAX_CONCURRENT_OPERATIONS));
+
+/// Название типа документа, если его документы проводятся пакетно и по расписанию.
+pub fn document_type_label(entity_type: &str) -> Option<&'static str> {
+    DOCUMENT_TYPES
+        .iter()
+        .find(|(code, _)| *code == entity_type)
+        .map(|(_, label)| *label)
+}
+
+/// Счётчик обработанных элементов операции. Исполнитель вызывает [`Progress::advance`]
+/// после каждого элемента; в `sys_operations` значение пишется не чаще
+/// [`PROGRESS_FLUSH_INTERVAL`], окончательное — вместе с итогом.
+pub struct Progress {
+    operation_id: String,
+    processed: usize,
+    flushed_at: Instant,
+}
+
+impl Progress {
+    fn new(operation_id: &str) -> Self {
+        Self {
+            operation_id: operation_id.to_string(),
+            processed: 0,
+            flushed_at: Instant::now(),
+        }
+    }
+
+    pub async fn advance(&mut self) {
+        self.processed += 1;
+        if self.flushed_at.elapsed() < PROGRESS_FLUSH_INTERVAL {
+            return;
+        }
+        self.flushed_at = Instant::now();
+        if let Err(e) = repository::set_progress(&self.operation_id, self.processed as i64).await {
+            tracing::warn!(
+                "[operation {}] failed to save progress: {e}",
+                self.operation_id
+            );
+        }
+    }
+}
+
+fn title(request: &OperationRequest) -> String {
+    let count = request.item_count();
+    match request {
+        OperationRequest::PostDocuments { entity_type, .. } => format!(
+            "Проведение: {}, {count} док.",
+            document_type_label(entity_type).unwrap_or(entity_type)
+        ),
+        OperationRequest::UnpostDocuments { entity_type, .. } => format!(
+            "Отмена проведения: {}, {count} док.",
+            document_type_label(entity_type).unwrap_or(entity_type)
+        ),
+        OperationRequest::PostByList { .. } => {
+            format!("Проведение по списку номеров, {count} док.")
+        }
+        OperationRequest::TagByList { tag, .. } => {
+            format!("Метка «{}» по списку номеров, {count} док.", tag.trim())
+        }
+    }
+}
+
+/// Проверка запроса до постановки в очередь; текст ошибки — для пользователя.
+pub fn validate(request: &OperationRequest) -> Result<()> {
+    let count = request.item_count();
+    if count == 0 {
+        bail!("Не выбрано ни одного документа");
+    }
+    if count > OPERATION_MAX_ITEMS {
+        bail!(
+            "Слишком много документов: {} (максимум {})",
+            count,
+            OPERATION_MAX_ITEMS
+        );
+    }
+    match request {
+        OperationRequest::PostDocuments { entity_type, .. }
+        | OperationRequest::UnpostDocuments { entity_type, .. } => {
+            if document_type_label(entity_type).is_none() {
+                bail!("Пакетное проведение не поддерживается для '{entity_type}'");
+            }
+        }
+        OperationRequest::PostByList { .. } => {}
+        OperationRequest::TagByList { tag, .. } => {
+            bulk_ops::lookup::normalize_tag(tag)?;
+        }
+    }
+    Ok(())
+}
+
+/// Может ли пользователь запустить операцию: проведение — при полном доступе к агрегату
+/// (как у одиночного проведения), действия по списку номеров — только администратор.
+pub async fn is_allowed(request: &OperationRequest, claims: &TokenClaims) -> Result<bool> {
+    if claims.is_admin {
+        return Ok(true);
+    }
+    match request {
+        OperationRequest::PostDocuments { entity_type, .. }
+        | OperationRequest::UnpostDocuments { entity_type, .. } => {
+            can_post(entity_type, claims).await
+        }
+        OperationRequest::PostByList { .. } | OperationRequest::TagByList { .. } => Ok(false),
+    }
+}
+
+/// Полный доступ к агрегату — то же право, что у кнопки «Провести» в документе.
+pub async fn can_post(entity_type: &str, claims: &TokenClaims) -> Result<bool> {
+    if claims.is_admin {
+        return Ok(true);
+    }
+    let scopes = crate::system::access::resolver::resolve_user_scopes(&claims.sub).await?;
+    Ok(scopes
+        .iter()
+        .any(|s| s.scope_id == entity_type && s.mode == "all"))
+}
+
+fn to_dto(model: repository::Model) -> OperationDto {
+    OperationDto {
+        status: OperationStatus::from_code(&model.status).unwrap_or(OperationStatus::Failed),
+        result: model
+            .result_json
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok()),
+        processed: model.processed.max(0) as usize,
+        total: model.total.max(0) as usize,
+        id: model.id,
+        kind: model.kind,
+        title: model.title,
+        error: model.error,
+        created_at: model.created_at,
+        started_at: model.started_at,
+        finished_at: model.finished_at,
+    }
+}
+
+/// Ставит операцию в очередь и сразу возвращает её; запрос должен пройти [`validate`].
+pub async fn start(request: OperationRequest, user_id: &str) -> Result<OperationDto> {
+    let model = repository::Model {
+        id: Uuid::new_v4().to_string(),
+        user_id: user_id.to_string(),
+        kind: request.kind().to_string(),
+        title: title(&request),
+        request_json: serde_json::to_string(&request)?,
+        status: OperationStatus::Queued.code().to_string(),
+        processed: 0,
+        total: request.item_count() as i64,
+        result_json: None,
+        error: None,
+        created_at: Utc::now().to_rfc3339(),
+        started_at: None,
+        finished_at: None,
+    };
+    repository::insert(model.clone()).await?;
+
+    let operation = model.clone();
+    tokio::spawn(async move {
+        run(operation, request).await;
+    });
+
+    Ok(to_dto(model))
+}
+
+async fn run(operation: repository::Model, request: OperationRequest) {
+    let Ok(_slot) = OPERATION_SLOTS.acquire().await else {
+        return;
+    };
+    if let Err(e) = repository::set_running(
+        &operation.id,
+        OperationStatus::Running.code(),
+        &Utc::now().to_rfc3339(),
+    )
+    .await
+    {
+        tracing::warn!("[operation {}] failed to mark running: {e}", operation.id);
+    }
+
+    let started = Instant::now();
+    let mut progress = Progress::new(&operation.id);
+    // Проведения в фоне пишутся в журнал «История» от имени автора операции
+    let outcome = document_audit::as_user(
+        &operation.user_id,
+        execute(&request, &operation.user_id, &mut progress),
+    )
+    .await;
+    let finished_at = Utc::now().to_rfc3339();
+    let saved = match outcome {
+        Ok(result) => {
+            tracing::info!(
+                "[operation {}] {} done in {:.1}s: succeeded {}, failed {}",
+                operation.id,
+                operation.kind,
+                started.elapsed().as_secs_f64(),
+                result.succeeded,
+                result.failed
+            );
+            repository::finish(
+                &operation.id,
+                OperationStatus::Completed.code(),
+                progress.processed as i64,
+                serde_json::to_string(&result).ok(),
+                None,
+                &finished_at,
+            )
+            .await
+        }
+        Err(e) => {
+            tracing::error!(
+                "[operation {}] {} failed: {e}",
+                operation.id,
+                operation.kind
+            );
+            repository::finish(
+                &operation.id,
+                OperationStatus::Failed.code(),
+                progress.processed as i64,
+                None,
+                Some(e.to_string()),
+                &finished_at,
+            )
+            .await
+        }
+    };
+    if let Err(e) = saved {
+        tracing::warn!("[operation {}] failed to save result: {e}", operation.id);
+    }
+}
+
+async fn execute(
+    request: &OperationRequest,
+    user_id: &str,
+    progress: &mut Progress,
+) -> Result<BulkActionResultDto> {
+    match request {
+        OperationRequest::PostDocuments { entity_type, ids } => {
+            post_documents(entity_type, ids, progress).await
+        }
+        OperationRequest::UnpostDocuments { entity_type, ids } => {
+            unpost_documents(entity_type, ids, user_id, progress).await
+        }
+        OperationRequest::PostByList { documents } => {
+            bulk_ops::lookup::post_documents(documents, progress).await
+        }
+        OperationRequest::TagByList { documents, tag } => {
+            bulk_ops::lookup::tag_documents(documents, tag, user_id, progress).await
+        }
+    }
+}
+
+fn push_error(result: &mut BulkActionResultDto, id: &str, e: anyhow::Error) {
+    result.failed += 1;
+    if result.errors.len() < MAX_ERRORS {
+        result.errors.push(format!("{id}: {e}"));
+    }
+}
+
+async fn post_documents(
+    entity_type: &str,
+    ids: &[String],
+    progress: &mut Progress,
+) -> Result<BulkActionResultDto> {
+    let mut result = BulkActionResultDto::default();
+    for id_str in ids {
+        let outcome = match Uuid::parse_str(id_str) {
+            Ok(id) => bulk_ops::service::post_document(entity_type, id).await,
+            Err(e) => Err(e.into()),
+        };
+        match outcome {
+            Ok(()) => result.succeeded += 1,
+            Err(e) => push_error(&mut result, id_str, e),
+        }
+        progress.advance().await;
+    }
+    Ok(result)
+}
+
+/// Отмена проведения; документы, которые были проведены до операции, записываются
+/// снимком в журнал массовых операций — их можно вернуть отменой в течение окна.
+async fn unpost_documents(
+    entity_type: &str,
+    ids: &[String],
+    user_id: &str,
+    progress: &mut Progress,
+) -> Result<BulkActionResultDto> {
+    let mut result = BulkActionResultDto::default();
+    let mut unposted: Vec<Uuid> = Vec::new();
+    for id_str in ids {
+        let outcome = match Uuid::parse_str(id_str) {
+            Ok(id) => {
+                let was_posted = bulk_ops::service::is_posted(entity_type, id)
+                    .await
+                    .unwrap_or(false);
+                bulk_ops::service::unpost_document(entity_type, id)
+                    .await
+                    .map(|()| was_posted.then_some(id))
+            }
+            Err(e) => Err(e.into()),
+        };
+        match outcome {
+            Ok(snapshot) => {
+                result.succeeded += 1;
+                unposted.extend(snapshot);
+            }
+            Err(e) => push_error(&mut result, id_str, e),
+        }
+        progress.advance().await;
+    }
+    if let Err(e) = bulk_ops::service::record_unpost(entity_type, &unposted, user_id).await {
+        tracing::error!("Failed to record bulk unpost for undo: {}", e);
+    }
+    Ok(result)
+}
+
+/// Операция пользователя; `None` — нет такой операции или она чужая.
+pub async fn get(id: &str, user_id: &str) -> Result<Option<OperationDto>> {
+    Ok(repository::get_by_id(id)
+        .await?
+        .filter(|model| model.user_id == user_id)
+        .map(to_dto))
+}
+
+pub async fn list_for_user(user_id: &str) -> Result<OperationListResponse> {
+    Ok(OperationListResponse {
+        items: repository::list_for_user(user_id, LIST_LIMIT)
+            .await?
+            .into_iter()
+            .map(to_dto)
+            .collect(),
+    })
+}
+
+/// Операции, которые выполнялись при остановке сервера, уже не завершатся.
+pub async fn fail_interrupted() -> Result<u64> {
+    Ok(repository::fail_unfinished(
+        &[
+            OperationStatus::Queued.code(),
+            OperationStatus::Running.code(),
+        ],
+        OperationStatus::Failed.code(),
+        "Прервано перезапуском сервера",
+        &Utc::now().to_rfc3339(),
+    )
+    .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::system::bulk_ops::BulkDocumentRef;
+
+    fn post(entity_type: &str, count: usize) -> OperationRequest {
+        OperationRequest::PostDocuments {
+            entity_type: entity_type.to_string(),
+            ids: (0..count).map(|i| i.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn validate_rejects_empty_oversized_and_unknown_requests() {
+        assert!(validate(&post("a012_wb_sales", 3)).is_ok());
+        assert!(validate(&post("a012_wb_sales", 0)).is_err());
+        assert!(validate(&post("a012_wb_sales", OPERATION_MAX_ITEMS + 1)).is_err());
+        assert!(validate(&post("a015_wb_orders", 1)).is_err());
+        let tag = |tag: &str| OperationRequest::TagByList {
+            documents: vec![BulkDocumentRef {
+                entity_type: "a012_wb_sales".to_string(),
+                id: "1".to_string(),
+            }],
+            tag: tag.to_string(),
+        };
+        assert!(validate(&tag("проверено")).is_ok());
+        assert!(validate(&tag("   ")).is_err());
+    }
+
+    #[test]
+    fn title_names_document_type_and_count() {
+        assert_eq!(
+            title(&post("a013_ym_order", 12)),
+            "Проведение: Заказы Яндекс Маркета, 12 док."
+        );
+    }
+}
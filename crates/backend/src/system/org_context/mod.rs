@@ -0,0 +1,20 @@
+//! Организационный контекст запроса.
+//!
+//! Пользователь выбирает в шапке активную организацию (`sys_users.active_organization_ref`);
+//! middleware авторизации кладёт её в extensions как [`service::ActiveOrganization`], а
+//! обработчики списков и карточек документов ограничивают ею выборку. Если организация
+//! задана хостом (брендирование), она важнее выбора пользователя.
+//!
+//! Доступ определяется членством (`sys_user_organizations`), а не хостом и не выбором:
+//! не-администратор работает только с организациями, которые ему назначил
+//! администратор. Заголовок `Host` задаёт клиент, поэтому организация хоста без
+//! членства просто игнорируется.
+//!
+//! Документы без организации (справочники, агрегаты без кабинета) не фильтруются.
+//! Фоновые выгрузки (export profiles) ограничиваются членствами владельца профиля.
+//! Лента CDC внешнего API вне контекста: у серверного ключа DWH нет пользователя.
+
+pub mod repository;
+pub mod service;
+
+pub use service::ActiveOrganization;
@@ -0,0 +1,81 @@
+use anyhow::Result;
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement, TransactionTrait};
+
+use crate::shared::data::db::get_connection;
+
+/// Выбранная пользователем организация; `None` — все организации.
+pub async fn get_active(user_id: &str) -> Result<Option<String>> {
+    let row = get_connection()
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT active_organization_ref FROM sys_users WHERE id = ?",
+            [user_id.into()],
+        ))
+        .await?;
+    Ok(match row {
+        Some(row) => row.try_get::<Option<String>>("", "active_organization_ref")?,
+        None => None,
+    })
+}
+
+pub async fn set_active(user_id: &str, organization_id: Option<&str>) -> Result<()> {
+    get_connection()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_users SET active_organization_ref = ?, updated_at = ? WHERE id = ?",
+            [
+                organization_id.map(str::to_string).into(),
+                chrono::Utc::now().to_rfc3339().into(),
+                user_id.into(),
+            ],
+        ))
+        .await?;
+    Ok(())
+}
+
+/// Организации, доступные пользователю (`sys_user_organizations`).
+pub async fn list_memberships(user_id: &str) -> Result<Vec<String>> {
+    let rows = get_connection()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT organization_id FROM sys_user_organizations WHERE user_id = ? \
+             ORDER BY organization_id",
+            [user_id.into()],
+        ))
+        .await?;
+    rows.into_iter()
+        .map(|row| Ok(row.try_get::<String>("", "organization_id")?))
+        .collect()
+}
+
+/// Заменяет список доступных пользователю организаций одной транзакцией.
+pub async fn replace_memberships(
+    user_id: &str,
+    organization_ids: &[String],
+    created_by: &str,
+) -> Result<()> {
+    let txn = get_connection().begin().await?;
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM sys_user_organizations WHERE user_id = ?",
+        [user_id.into()],
+    ))
+    .await?;
+    let now = chrono::Utc::now().to_rfc3339();
+    for organization_id in organization_ids {
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT OR IGNORE INTO sys_user_organizations \
+             (user_id, organization_id, created_at, created_by) VALUES (?, ?, ?, ?)",
+            [
+                user_id.into(),
+                organization_id.clone().into(),
+                now.clone().into(),
+                created_by.into(),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+    Ok(())
+}
@@ -0,0 +1,488 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, Extensions},
+};
+use contracts::system::org_context::{
+    ActiveOrganizationDto, ActiveOrganizationUpdateRequest, OrganizationOptionDto,
+    UserOrganizationsDto,
+};
+use once_cell::sync::Lazy;
+
+use super::repository;
+use crate::system::branding;
+use crate::system::quotas::service::CurrentOrganization;
+
+/// Выбор пользователя перечитывается не чаще раза в минуту; смена через API
+/// сбрасывает кэш сразу.
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+static SELECTED: Lazy<RwLock<HashMap<String, (Option<String>, Instant)>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+static MEMBERSHIPS: Lazy<RwLock<HashMap<String, (Vec<String>, Instant)>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Действующие организации и организация по умолчанию из брендирования.
+type OrganizationsSnapshot = (Vec<(String, String)>, Option<String>);
+
+static ORGANIZATIONS: Lazy<RwLock<Option<(OrganizationsSnapshot, Instant)>>> =
+    Lazy::new(|| RwLock::new(None));
+
+/// Организация, данными которой ограничен запрос; `None` — все организации
+/// (только для администратора).
+///
+/// Извлекается в обработчиках как `active_org: ActiveOrganization`. Кладётся в
+/// extensions middleware авторизации (`check_scope`, `require_auth`); без него —
+/// все организации.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActiveOrganization(pub Option<String>);
+
+/// Ссылки на организацию в документах встречаются в кавычках и разном регистре —
+/// сравнение как в SQL-фильтрах списков: `LOWER(TRIM(REPLACE(x, '"', '')))`.
+fn normalize(organization_id: &str) -> String {
+    organization_id.replace('"', "").trim().to_lowercase()
+}
+
+impl ActiveOrganization {
+    /// Фильтр организации для списка. При выбранной организации — всегда она:
+    /// параметр запроса не может ни расширить выборку, ни подменить организацию.
+    pub fn scope(&self, requested: Option<String>) -> Option<String> {
+        match &self.0 {
+            Some(active) => Some(active.clone()),
+            None => requested,
+        }
+    }
+
+    /// Документ организации `organization_id` виден в текущем контексте.
+    pub fn allows(&self, organization_id: &str) -> bool {
+        self.0.as_deref().map_or(true, |active| {
+            normalize(active) == normalize(organization_id)
+        })
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ActiveOrganization
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<ActiveOrganization>()
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+async fn selected_for_user(user_id: &str) -> Result<Option<String>> {
+    if let Some((selected, loaded_at)) = SELECTED
+        .read()
+        .ok()
+        .and_then(|cache| cache.get(user_id).cloned())
+    {
+        if loaded_at.elapsed() < CACHE_TTL {
+            return Ok(selected);
+        }
+    }
+    let selected = repository::get_active(user_id).await?;
+    if let Ok(mut cache) = SELECTED.write() {
+        cache.insert(user_id.to_string(), (selected.clone(), Instant::now()));
+    }
+    Ok(selected)
+}
+
+/// Организации, назначенные пользователю. Фоновые задачи от имени пользователя
+/// (профили выгрузок) ограничиваются ими — активной организации у них нет.
+pub async fn member_organizations(user_id: &str) -> Result<Vec<String>> {
+    memberships_for_user(user_id).await
+}
+
+async fn memberships_for_user(user_id: &str) -> Result<Vec<String>> {
+    if let Some((members, loaded_at)) = MEMBERSHIPS
+        .read()
+        .ok()
+        .and_then(|cache| cache.get(user_id).cloned())
+    {
+        if loaded_at.elapsed() < CACHE_TTL {
+            return Ok(members);
+        }
+    }
+    let members = repository::list_memberships(user_id).await?;
+    if let Ok(mut cache) = MEMBERSHIPS.write() {
+        cache.insert(user_id.to_string(), (members.clone(), Instant::now()));
+    }
+    Ok(members)
+}
+
+fn is_member(members: &[String], organization_id: &str) -> bool {
+    members
+        .iter()
+        .any(|member| normalize(member) == normalize(organization_id))
+}
+
+/// Организация по умолчанию для пользователя, которому «все организации»
+/// недоступны: отмеченная `is_default` в брендировании, иначе первая по
+/// наименованию. `organizations` — только доступные пользователю, удалённые
+/// организации не подходят.
+fn default_organization(
+    organizations: &[(String, String)],
+    branding_default: Option<&str>,
+) -> Option<String> {
+    branding_default
+        .and_then(|preferred| {
+            organizations
+                .iter()
+                .find(|(id, _)| normalize(id) == normalize(preferred))
+        })
+        .or_else(|| organizations.first())
+        .map(|(id, _)| id.clone())
+}
+
+async fn load_organizations() -> Result<OrganizationsSnapshot> {
+    if let Some((snapshot, loaded_at)) = ORGANIZATIONS.read().ok().and_then(|cache| cache.clone()) {
+        if loaded_at.elapsed() < CACHE_TTL {
+            return Ok(snapshot);
+        }
+    }
+    let organizations = branding::repository::list_organizations().await?;
+    let branding_default = branding::repository::list_all()
+        .await?
+        .into_iter()
+        .find(|row| row.is_default)
+        .map(|row| row.organization_id);
+    let snapshot = (organizations, branding_default);
+    if let Ok(mut cache) = ORGANIZATIONS.write() {
+        *cache = Some((snapshot.clone(), Instant::now()));
+    }
+    Ok(snapshot)
+}
+
+async fn load_default_organization(members: &[String]) -> Result<Option<String>> {
+    let (organizations, branding_default) = load_organizations().await?;
+    let available: Vec<(String, String)> = organizations
+        .into_iter()
+        .filter(|(id, _)| is_member(members, id))
+        .collect();
+    Ok(default_organization(
+        &available,
+        branding_default.as_deref(),
+    ))
+}
+
+/// Организация доступна пользователю: администратору — любая, остальным —
+/// только из `sys_user_organizations`.
+fn permitted(is_admin: bool, members: &[String], organization_id: &str) -> bool {
+    is_admin || is_member(members, organization_id)
+}
+
+/// Организация хоста важнее выбора пользователя, но ни хост, ни выбор не дают
+/// доступа сверх членства: недоступная организация пропускается. «Все
+/// организации» — только администратору; остальным — организация по умолчанию
+/// из доступных, а без неё `None` (запрос отклоняется).
+fn resolve(
+    host: Option<&str>,
+    selected: Option<String>,
+    is_admin: bool,
+    members: &[String],
+    default: Option<String>,
+) -> Option<ActiveOrganization> {
+    let chosen = host
+        .map(str::to_string)
+        .filter(|id| permitted(is_admin, members, id))
+        .or(selected.filter(|id| permitted(is_admin, members, id)));
+    match chosen {
+        Some(organization_id) => Some(ActiveOrganization(Some(organization_id))),
+        None if is_admin => Some(ActiveOrganization(None)),
+        None => default
+            .filter(|id| is_member(members, id))
+            .map(|organization_id| ActiveOrganization(Some(organization_id))),
+    }
+}
+
+/// Организация запроса: хост, выбор пользователя, для не-администратора без
+/// доступного выбора — организация по умолчанию из доступных ему.
+async fn effective(
+    user_id: &str,
+    is_admin: bool,
+    host: Option<&str>,
+    selected: impl std::future::Future<Output = Result<Option<String>>>,
+    members: impl std::future::Future<Output = Result<Vec<String>>>,
+) -> Result<ActiveOrganization> {
+    let members = if is_admin { Vec::new() } else { members.await? };
+    let selected = selected.await?;
+    let chosen = host.is_some_and(|id| permitted(is_admin, &members, id))
+        || selected
+            .as_deref()
+            .is_some_and(|id| permitted(is_admin, &members, id));
+    let default = if chosen || is_admin {
+        None
+    } else {
+        load_default_organization(&members).await?
+    };
+    resolve(host, selected, is_admin, &members, default)
+        .ok_or_else(|| anyhow!("Forbidden: no organization available for user {}", user_id))
+}
+
+/// Кладёт в extensions организацию запроса пользователя `user_id`. Ошибка чтения
+/// выбора не превращается в «все организации» — запрос отклоняется.
+pub async fn attach(user_id: &str, is_admin: bool, extensions: &mut Extensions) -> Result<()> {
+    let host = extensions
+        .get::<CurrentOrganization>()
+        .map(|CurrentOrganization(id)| id.clone());
+    let active = effective(
+        user_id,
+        is_admin,
+        host.as_deref(),
+        selected_for_user(user_id),
+        memberships_for_user(user_id),
+    )
+    .await?;
+    extensions.insert(active);
+    Ok(())
+}
+
+pub async fn get(
+    user_id: &str,
+    is_admin: bool,
+    host: Option<&str>,
+) -> Result<ActiveOrganizationDto> {
+    let members = if is_admin {
+        Vec::new()
+    } else {
+        repository::list_memberships(user_id).await?
+    };
+    let ActiveOrganization(organization_id) = effective(
+        user_id,
+        is_admin,
+        host,
+        repository::get_active(user_id),
+        std::future::ready(Ok::<_, anyhow::Error>(members.clone())),
+    )
+    .await?;
+    let organizations: Vec<OrganizationOptionDto> = branding::repository::list_organizations()
+        .await?
+        .into_iter()
+        .filter(|(id, _)| permitted(is_admin, &members, id))
+        .map(|(id, name)| OrganizationOptionDto { id, name })
+        .collect();
+    let organization_name = organization_id.as_deref().and_then(|id| {
+        organizations
+            .iter()
+            .find(|o| normalize(&o.id) == normalize(id))
+            .map(|o| o.name.clone())
+    });
+    Ok(ActiveOrganizationDto {
+        organization_id,
+        organization_name,
+        locked_by_host: host.is_some_and(|id| permitted(is_admin, &members, id)),
+        can_select_all: is_admin,
+        organizations,
+    })
+}
+
+pub async fn set(
+    user_id: &str,
+    is_admin: bool,
+    host: Option<&str>,
+    req: ActiveOrganizationUpdateRequest,
+) -> Result<ActiveOrganizationDto> {
+    let members = if is_admin {
+        Vec::new()
+    } else {
+        repository::list_memberships(user_id).await?
+    };
+    if host.is_some_and(|id| permitted(is_admin, &members, id)) {
+        return Err(anyhow!("Forbidden: organization is defined by host"));
+    }
+    let organization_id = req
+        .organization_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    if organization_id.is_none() && !is_admin {
+        return Err(anyhow!(
+            "Forbidden: all organizations are available to admins only"
+        ));
+    }
+    if let Some(id) = organization_id.as_deref() {
+        let exists = branding::repository::list_organizations()
+            .await?
+            .iter()
+            .any(|(known, _)| normalize(known) == normalize(id));
+        if !exists {
+            return Err(anyhow!("Organization not found"));
+        }
+        if !permitted(is_admin, &members, id) {
+            return Err(anyhow!("Forbidden: organization is not available to user"));
+        }
+    }
+    repository::set_active(user_id, organization_id.as_deref()).await?;
+    if let Ok(mut cache) = SELECTED.write() {
+        cache.remove(user_id);
+    }
+    get(user_id, is_admin, None).await
+}
+
+/// Организации, доступные пользователю `user_id` (карточка пользователя).
+pub async fn get_memberships(user_id: &str) -> Result<UserOrganizationsDto> {
+    Ok(UserOrganizationsDto {
+        organization_ids: repository::list_memberships(user_id).await?,
+    })
+}
+
+/// Заменяет доступные пользователю организации; неизвестные организации отклоняются.
+/// Выбор пользователя вне нового списка перестаёт действовать сразу.
+pub async fn set_memberships(
+    user_id: &str,
+    dto: UserOrganizationsDto,
+    updated_by: &str,
+) -> Result<UserOrganizationsDto> {
+    let known = branding::repository::list_organizations().await?;
+    let mut organization_ids: Vec<String> = Vec::new();
+    for id in dto.organization_ids {
+        let Some((known_id, _)) = known.iter().find(|(k, _)| normalize(k) == normalize(&id)) else {
+            return Err(anyhow!("Organization not found: {}", id));
+        };
+        if !organization_ids.contains(known_id) {
+            organization_ids.push(known_id.clone());
+        }
+    }
+    repository::replace_memberships(user_id, &organization_ids, updated_by).await?;
+    if let Ok(mut cache) = MEMBERSHIPS.write() {
+        cache.remove(user_id);
+    }
+    get_memberships(user_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWN: &str = "5f0c8a8e-1111-4a4a-9b9b-000000000001";
+    const OTHER: &str = "5f0c8a8e-2222-4a4a-9b9b-000000000002";
+
+    fn active(id: &str) -> ActiveOrganization {
+        ActiveOrganization(Some(id.to_string()))
+    }
+
+    #[test]
+    fn list_filter_never_leaves_active_organization() {
+        let org = active(OWN);
+        assert_eq!(org.scope(None).as_deref(), Some(OWN));
+        assert_eq!(org.scope(Some(OWN.to_string())).as_deref(), Some(OWN));
+        assert_eq!(org.scope(Some(OTHER.to_string())).as_deref(), Some(OWN));
+        assert_eq!(org.scope(Some(String::new())).as_deref(), Some(OWN));
+    }
+
+    #[test]
+    fn all_organizations_keep_requested_filter() {
+        let all = ActiveOrganization::default();
+        assert_eq!(all.scope(None), None);
+        assert_eq!(all.scope(Some(OTHER.to_string())).as_deref(), Some(OTHER));
+        assert!(all.allows(OWN));
+        assert!(all.allows(OTHER));
+    }
+
+    #[test]
+    fn documents_of_other_organizations_are_hidden() {
+        let org = active(OWN);
+        assert!(org.allows(OWN));
+        assert!(org.allows(&format!("\"{}\"", OWN.to_uppercase())));
+        assert!(!org.allows(OTHER));
+        assert!(!org.allows(""));
+    }
+
+    fn members(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn host_organization_overrides_user_choice() {
+        let both = members(&[OWN, OTHER]);
+        assert_eq!(
+            resolve(Some(OTHER), Some(OWN.to_string()), false, &both, None),
+            Some(active(OTHER))
+        );
+        assert_eq!(
+            resolve(None, Some(OWN.to_string()), false, &both, None),
+            Some(active(OWN))
+        );
+        assert_eq!(
+            resolve(None, None, true, &[], Some(OWN.to_string())),
+            Some(ActiveOrganization::default())
+        );
+    }
+
+    #[test]
+    fn host_and_choice_never_grant_foreign_organization() {
+        let own = members(&[OWN]);
+        // Подставленный Host чужой организации не действует
+        assert_eq!(
+            resolve(Some(OTHER), Some(OWN.to_string()), false, &own, None),
+            Some(active(OWN))
+        );
+        // Выбор, сделанный до лишения доступа, тоже
+        assert_eq!(
+            resolve(
+                None,
+                Some(OTHER.to_string()),
+                false,
+                &own,
+                Some(OWN.to_string())
+            ),
+            Some(active(OWN))
+        );
+        assert_eq!(
+            resolve(Some(OTHER), Some(OTHER.to_string()), false, &own, None),
+            None
+        );
+        // Администратору доступна любая
+        assert_eq!(
+            resolve(Some(OTHER), None, true, &[], None),
+            Some(active(OTHER))
+        );
+    }
+
+    #[test]
+    fn only_admin_gets_all_organizations() {
+        let own = members(&[OWN]);
+        assert_eq!(
+            resolve(None, None, false, &own, Some(OWN.to_string())),
+            Some(active(OWN))
+        );
+        assert_eq!(resolve(None, None, false, &own, None), None);
+        // Без членства организация по умолчанию не выдаётся
+        assert_eq!(resolve(None, None, false, &[], Some(OWN.to_string())), None);
+    }
+
+    #[test]
+    fn default_organization_prefers_branding_default() {
+        let organizations = vec![
+            (OWN.to_string(), "Альфа".to_string()),
+            (OTHER.to_string(), "Бета".to_string()),
+        ];
+        assert_eq!(
+            default_organization(&organizations, Some(OTHER)).as_deref(),
+            Some(OTHER)
+        );
+        assert_eq!(
+            default_organization(&organizations, None).as_deref(),
+            Some(OWN)
+        );
+        // Организация по умолчанию удалена — первая по наименованию
+        assert_eq!(
+            default_organization(&organizations, Some("deleted")).as_deref(),
+            Some(OWN)
+        );
+        assert_eq!(default_organization(&[], Some(OWN)), None);
+    }
+}
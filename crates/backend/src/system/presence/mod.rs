@@ -0,0 +1,202 @@
+//! Присутствие пользователей на формах документов.
+//!
+//! Состояние живёт в памяти процесса: открытое SSE-соединение формы (`/presence/stream`)
+//! означает «пользователь смотрит документ», флаг редактирования и факт сохранения
+//! приходят отдельными запросами. Любое изменение по документу рассылается всем его
+//! подписчикам через broadcast-канал; при закрытии последнего соединения пользователь
+//! исчезает из снимка, а документ без зрителей удаляется из памяти.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::Utc;
+use contracts::system::presence::{PresenceSavedDto, PresenceSnapshot, PresenceUserDto};
+use once_cell::sync::Lazy;
+use tokio::sync::broadcast;
+
+struct Viewer {
+    username: String,
+    /// Несколько вкладок/окон одного пользователя на одном документе
+    connections: usize,
+    editing: bool,
+    since: String,
+}
+
+#[derive(Default)]
+struct DocState {
+    viewers: HashMap<String, Viewer>,
+    last_saved: Option<PresenceSavedDto>,
+}
+
+static DOCS: Lazy<Mutex<HashMap<String, DocState>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+/// Ключи документов, по которым изменилось присутствие
+static CHANGES: Lazy<broadcast::Sender<String>> = Lazy::new(|| broadcast::channel(256).0);
+
+fn doc_key(entity: &str, entity_id: &str) -> String {
+    format!("{entity}:{entity_id}")
+}
+
+fn notify(key: &str) {
+    // Ошибка означает лишь отсутствие подписчиков
+    let _ = CHANGES.send(key.to_string());
+}
+
+/// Открытое соединение пользователя с документом; при drop пользователь уходит.
+pub struct Connection {
+    key: String,
+    user_id: String,
+}
+
+impl Connection {
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        let mut docs = DOCS.lock().unwrap();
+        if let Some(doc) = docs.get_mut(&self.key) {
+            if let Some(viewer) = doc.viewers.get_mut(&self.user_id) {
+                viewer.connections = viewer.connections.saturating_sub(1);
+                if viewer.connections == 0 {
+                    doc.viewers.remove(&self.user_id);
+                }
+            }
+            if doc.viewers.is_empty() {
+                docs.remove(&self.key);
+            }
+        }
+        drop(docs);
+        notify(&self.key);
+    }
+}
+
+/// Пользователь открыл документ.
+pub fn join(entity: &str, entity_id: &str, user_id: &str, username: &str) -> Connection {
+    let key = doc_key(entity, entity_id);
+    {
+        let mut docs = DOCS.lock().unwrap();
+        let viewer = docs
+            .entry(key.clone())
+            .or_default()
+            .viewers
+            .entry(user_id.to_string())
+            .or_insert_with(|| Viewer {
+                username: username.to_string(),
+                connections: 0,
+                editing: false,
+                since: Utc::now().to_rfc3339(),
+            });
+        viewer.connections += 1;
+    }
+    notify(&key);
+    Connection {
+        key,
+        user_id: user_id.to_string(),
+    }
+}
+
+/// Подписка на изменения присутствия (ключи документов).
+pub fn subscribe() -> broadcast::Receiver<String> {
+    CHANGES.subscribe()
+}
+
+/// Отметка «есть несохранённые изменения»; без открытого соединения игнорируется.
+pub fn set_editing(entity: &str, entity_id: &str, user_id: &str, editing: bool) {
+    let key = doc_key(entity, entity_id);
+    let changed = {
+        let mut docs = DOCS.lock().unwrap();
+        match docs
+            .get_mut(&key)
+            .and_then(|doc| doc.viewers.get_mut(user_id))
+        {
+            Some(viewer) if viewer.editing != editing => {
+                viewer.editing = editing;
+                true
+            }
+            _ => false,
+        }
+    };
+    if changed {
+        notify(&key);
+    }
+}
+
+/// Документ сохранён пользователем: остальные зрители получат предупреждение.
+pub fn record_saved(entity: &str, entity_id: &str, user_id: &str, username: &str) {
+    let key = doc_key(entity, entity_id);
+    {
+        let mut docs = DOCS.lock().unwrap();
+        let Some(doc) = docs.get_mut(&key) else {
+            return;
+        };
+        doc.last_saved = Some(PresenceSavedDto {
+            user_id: user_id.to_string(),
+            username: username.to_string(),
+            saved_at: Utc::now().to_rfc3339(),
+        });
+        if let Some(viewer) = doc.viewers.get_mut(user_id) {
+            viewer.editing = false;
+        }
+    }
+    notify(&key);
+}
+
+/// Текущий снимок по документу (пользователи — в порядке открытия).
+pub fn snapshot(entity: &str, entity_id: &str) -> PresenceSnapshot {
+    let docs = DOCS.lock().unwrap();
+    let doc = docs.get(&doc_key(entity, entity_id));
+    let mut users: Vec<PresenceUserDto> = doc
+        .map(|doc| {
+            doc.viewers
+                .iter()
+                .map(|(user_id, viewer)| PresenceUserDto {
+                    user_id: user_id.clone(),
+                    username: viewer.username.clone(),
+                    editing: viewer.editing,
+                    since: viewer.since.clone(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    users.sort_by(|a, b| a.since.cmp(&b.since).then(a.username.cmp(&b.username)));
+    PresenceSnapshot {
+        entity: entity.to_string(),
+        entity_id: entity_id.to_string(),
+        users,
+        last_saved: doc.and_then(|doc| doc.last_saved.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connections_track_viewers_and_cleanup() {
+        let first = join("t_test", "doc-1", "u1", "ivanov");
+        let second_tab = join("t_test", "doc-1", "u1", "ivanov");
+        let other = join("t_test", "doc-1", "u2", "petrov");
+        set_editing("t_test", "doc-1", "u2", true);
+
+        let snap = snapshot("t_test", "doc-1");
+        assert_eq!(snap.users.len(), 2);
+        assert!(snap.users.iter().any(|u| u.user_id == "u2" && u.editing));
+
+        record_saved("t_test", "doc-1", "u2", "petrov");
+        let snap = snapshot("t_test", "doc-1");
+        assert!(snap.users.iter().all(|u| !u.editing));
+        assert!(snap
+            .saved_by_other_since("u1", &snap.users[0].since)
+            .is_some());
+
+        drop(first);
+        assert_eq!(snapshot("t_test", "doc-1").users.len(), 2);
+        drop(second_tab);
+        drop(other);
+        let empty = snapshot("t_test", "doc-1");
+        assert!(empty.users.is_empty());
+        assert!(empty.last_saved.is_none());
+    }
+}
@@ -0,0 +1,72 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{EntityTrait, QueryOrder, Set};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sys_print_templates")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub entity_type: String,
+    pub kind: String,
+    pub name: String,
+    pub body_html: String,
+    pub updated_at: String,
+    pub updated_by: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+fn conn() -> &'static DatabaseConnection {
+    get_connection()
+}
+
+/// Шаблоны, отсортированные по агрегату и названию; `entity_type` — фильтр.
+pub async fn list(entity_type: Option<&str>) -> Result<Vec<Model>, DbErr> {
+    let mut query = Entity::find();
+    if let Some(entity_type) = entity_type {
+        query = query.filter(Column::EntityType.eq(entity_type));
+    }
+    query
+        .order_by_asc(Column::EntityType)
+        .order_by_asc(Column::Name)
+        .all(conn())
+        .await
+}
+
+pub async fn get(id: &str) -> Result<Option<Model>, DbErr> {
+    Entity::find_by_id(id.to_string()).one(conn()).await
+}
+
+pub async fn upsert(model: Model) -> Result<(), DbErr> {
+    let exists = Entity::find_by_id(model.id.clone())
+        .one(conn())
+        .await?
+        .is_some();
+    let active = ActiveModel {
+        id: Set(model.id),
+        entity_type: Set(model.entity_type),
+        kind: Set(model.kind),
+        name: Set(model.name),
+        body_html: Set(model.body_html),
+        updated_at: Set(model.updated_at),
+        updated_by: Set(model.updated_by),
+    };
+    if exists {
+        active.update(conn()).await?;
+    } else {
+        active.insert(conn()).await?;
+    }
+    Ok(())
+}
+
+pub async fn delete(id: &str) -> Result<u64, DbErr> {
+    Ok(Entity::delete_by_id(id.to_string())
+        .exec(conn())
+        .await?
+        .rows_affected)
+}
@@ -0,0 +1,280 @@
+//! Печатные формы: ведение шаблонов (админ) и заполнение шаблона данными документа.
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use contracts::domain::a012_wb_sales::aggregate::WbSales;
+use contracts::domain::a015_wb_orders::aggregate::WbOrders;
+use contracts::shared::access::FinanceFields;
+use contracts::system::print_templates::{
+    print_template_entity, render_print_template, unknown_placeholders, PrintTemplateDto,
+    PrintTemplateKind, PrintTemplateUpsertRequest, RenderedPrintDocumentDto,
+    MAX_PRINT_TEMPLATE_LEN,
+};
+use uuid::Uuid;
+
+use super::repository;
+use crate::domain::{a002_organization, a004_nomenclature, a012_wb_sales, a015_wb_orders};
+use crate::shared::marketplaces::wildberries::datetime::wb_timezone;
+use crate::system::org_context::ActiveOrganization;
+
+const MAX_NAME_LEN: usize = 200;
+
+fn to_dto(model: repository::Model) -> Result<PrintTemplateDto> {
+    let kind = PrintTemplateKind::from_code(&model.kind)
+        .ok_or_else(|| anyhow!("Unknown print template kind: {}", model.kind))?;
+    Ok(PrintTemplateDto {
+        id: model.id,
+        entity_type: model.entity_type,
+        kind,
+        name: model.name,
+        body_html: model.body_html,
+        updated_at: model.updated_at,
+        updated_by: model.updated_by,
+    })
+}
+
+fn money(value: Option<f64>) -> String {
+    value.map(|v| format!("{v:.2}")).unwrap_or_default()
+}
+
+/// Реквизиты организации документа и дата печати.
+async fn common_values(organization_id: &str) -> Result<Vec<(&'static str, String)>> {
+    let organization = match Uuid::parse_str(organization_id) {
+        Ok(id) => a002_organization::service::get_by_id(id).await?,
+        Err(_) => None,
+    };
+    let today = Utc::now()
+        .with_timezone(&wb_timezone())
+        .format("%d.%m.%Y")
+        .to_string();
+    let (name, full_name, inn, kpp) = match organization {
+        Some(org) => (org.base.description, org.full_name, org.inn, org.kpp),
+        None => Default::default(),
+    };
+    Ok(vec![
+        ("organization_name", name),
+        ("organization_full_name", full_name),
+        ("organization_inn", inn),
+        ("organization_kpp", kpp),
+        ("today", today),
+    ])
+}
+
+async fn a012_values(doc: &WbSales) -> Result<Vec<(&'static str, String)>> {
+    let rrd_ids = a012_wb_sales::finance_links::list_rrd_ids(&doc.base.id.value().to_string())
+        .await?
+        .iter()
+        .map(i64::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut values = common_values(&doc.header.organization_id).await?;
+    values.extend([
+        ("document_no", doc.header.document_no.clone()),
+        ("sale_id", doc.header.sale_id.clone().unwrap_or_default()),
+        ("event_type", doc.state.event_type.clone()),
+        (
+            "sale_dt",
+            doc.state
+                .sale_dt
+                .with_timezone(&wb_timezone())
+                .format("%d.%m.%Y")
+                .to_string(),
+        ),
+        ("supplier_article", doc.line.supplier_article.clone()),
+        ("nm_id", doc.line.nm_id.to_string()),
+        ("barcode", doc.line.barcode.clone()),
+        ("product_name", doc.line.name.clone()),
+        ("qty", doc.line.qty.to_string()),
+        ("finished_price", money(doc.line.finished_price)),
+        ("amount_line", money(doc.line.amount_line)),
+        (
+            "supplier_payout",
+            money(
+                doc.line
+                    .supplier_payout_fact
+                    .or(doc.line.supplier_payout_plan),
+            ),
+        ),
+        (
+            "warehouse_name",
+            doc.warehouse.warehouse_name.clone().unwrap_or_default(),
+        ),
+        ("finance_rrd_ids", rrd_ids),
+    ]);
+    Ok(values)
+}
+
+/// Наименование товара заказа: из номенклатуры 1С, иначе предмет WB
+/// (в заказах WB наименования нет).
+async fn a015_product_name(doc: &WbOrders) -> Result<String> {
+    let nomenclature = match doc
+        .nomenclature_ref
+        .as_deref()
+        .and_then(|id| Uuid::parse_str(id).ok())
+    {
+        Some(id) => a004_nomenclature::service::get_by_id(id).await?,
+        None => None,
+    };
+    Ok(nomenclature
+        .map(|n| n.base.description)
+        .or_else(|| doc.line.subject.clone())
+        .unwrap_or_default())
+}
+
+async fn a015_values(doc: &WbOrders) -> Result<Vec<(&'static str, String)>> {
+    let mut values = common_values(&doc.header.organization_id).await?;
+    let product_name = a015_product_name(doc).await?;
+    values.extend([
+        ("document_no", doc.header.document_no.clone()),
+        (
+            "order_date",
+            doc.state
+                .order_dt
+                .with_timezone(&wb_timezone())
+                .format("%d.%m.%Y")
+                .to_string(),
+        ),
+        ("supplier_article", doc.line.supplier_article.clone()),
+        ("nm_id", doc.line.nm_id.to_string()),
+        ("barcode", doc.line.barcode.clone()),
+        ("product_name", product_name),
+        ("brand", doc.line.brand.clone().unwrap_or_default()),
+        ("subject", doc.line.subject.clone().unwrap_or_default()),
+        ("qty", doc.line.qty.to_string()),
+        ("finished_price", money(doc.line.finished_price)),
+        (
+            "warehouse_name",
+            doc.warehouse.warehouse_name.clone().unwrap_or_default(),
+        ),
+        (
+            "region_name",
+            doc.geography.region_name.clone().unwrap_or_default(),
+        ),
+        (
+            "income_id",
+            doc.source_meta
+                .income_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+        ),
+    ]);
+    Ok(values)
+}
+
+pub async fn list(entity_type: Option<&str>) -> Result<Vec<PrintTemplateDto>> {
+    repository::list(entity_type)
+        .await?
+        .into_iter()
+        .map(to_dto)
+        .collect()
+}
+
+pub async fn get(id: &str) -> Result<PrintTemplateDto> {
+    let model = repository::get(id)
+        .await?
+        .ok_or_else(|| anyhow!("Template not found"))?;
+    to_dto(model)
+}
+
+fn validate(req: &PrintTemplateUpsertRequest) -> Result<(String, String)> {
+    let entity = print_template_entity(&req.entity_type)
+        .ok_or_else(|| anyhow!("Invalid template: unknown entity {}", req.entity_type))?;
+    let name = req.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(anyhow!(
+            "Invalid template: name is required, up to {} characters",
+            MAX_NAME_LEN
+        ));
+    }
+    let body = req.body_html.trim();
+    if body.is_empty() || body.chars().count() > MAX_PRINT_TEMPLATE_LEN {
+        return Err(anyhow!(
+            "Invalid template: body is required, up to {} characters",
+            MAX_PRINT_TEMPLATE_LEN
+        ));
+    }
+    let unknown = unknown_placeholders(entity, body);
+    if !unknown.is_empty() {
+        return Err(anyhow!(
+            "Invalid template: unknown placeholders {}",
+            unknown.join(", ")
+        ));
+    }
+    Ok((name.to_string(), body.to_string()))
+}
+
+/// Создаёт шаблон (`id = None`) или заменяет существующий.
+pub async fn save(
+    id: Option<&str>,
+    req: &PrintTemplateUpsertRequest,
+    updated_by: &str,
+) -> Result<PrintTemplateDto> {
+    let (name, body_html) = validate(req)?;
+    let id = match id {
+        Some(id) => {
+            repository::get(id)
+                .await?
+                .ok_or_else(|| anyhow!("Template not found"))?;
+            id.to_string()
+        }
+        None => Uuid::new_v4().to_string(),
+    };
+    let model = repository::Model {
+        id,
+        entity_type: req.entity_type.clone(),
+        kind: req.kind.code().to_string(),
+        name,
+        body_html,
+        updated_at: Utc::now().to_rfc3339(),
+        updated_by: updated_by.to_string(),
+    };
+    repository::upsert(model.clone()).await?;
+    to_dto(model)
+}
+
+pub async fn delete(id: &str) -> Result<()> {
+    if repository::delete(id).await? == 0 {
+        return Err(anyhow!("Template not found"));
+    }
+    Ok(())
+}
+
+/// Заполняет шаблон данными документа его агрегата. Документ другой организации —
+/// как несуществующий; без доступа к финансам (`show_finance == false`) суммы
+/// документа стираются до заполнения, и их плейсхолдеры печатаются пустыми.
+pub async fn render(
+    template: &PrintTemplateDto,
+    document_id: &str,
+    active_org: &ActiveOrganization,
+    show_finance: bool,
+) -> Result<RenderedPrintDocumentDto> {
+    let uuid = Uuid::parse_str(document_id)
+        .map_err(|_| anyhow!("Invalid document id: {}", document_id))?;
+    let (document_no, values) = match template.entity_type.as_str() {
+        "a012_wb_sales" => {
+            let mut doc = a012_wb_sales::service::get_by_id(uuid)
+                .await?
+                .filter(|doc| active_org.allows(&doc.header.organization_id))
+                .ok_or_else(|| anyhow!("Document not found"))?;
+            if !show_finance {
+                doc.mask_finance();
+            }
+            (doc.header.document_no.clone(), a012_values(&doc).await?)
+        }
+        "a015_wb_orders" => {
+            let mut doc = a015_wb_orders::service::get_by_id(uuid)
+                .await?
+                .filter(|doc| active_org.allows(&doc.header.organization_id))
+                .ok_or_else(|| anyhow!("Document not found"))?;
+            if !show_finance {
+                doc.mask_finance();
+            }
+            (doc.header.document_no.clone(), a015_values(&doc).await?)
+        }
+        other => return Err(anyhow!("Entity not found: {}", other)),
+    };
+    Ok(RenderedPrintDocumentDto {
+        title: format!("{} {}", template.name, document_no),
+        html: render_print_template(&template.body_html, &values),
+    })
+}
@@ -0,0 +1,54 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use super::service::{self, CurrentOrganization};
+use crate::system::api::handlers::branding::request_host;
+
+/// Не учитываются: брендирование, режим окружения и вход (нужны до авторизации) и сама страница квот —
+/// чтобы администратор мог поднять лимит организации, исчерпавшей его.
+const EXEMPT_PREFIXES: &[&str] = &[
+    "/api/system/branding",
+    "/api/system/environment",
+    "/api/system/auth/",
+    "/api/sys/quotas",
+];
+
+fn is_metered(path: &str) -> bool {
+    path.starts_with("/api/") && !EXEMPT_PREFIXES.iter().any(|p| path.starts_with(p))
+}
+
+/// Учёт запросов к API по организации хоста и отказ 429 сверх суточной квоты.
+/// Организация запроса передаётся обработчикам как `Extension<CurrentOrganization>`.
+pub async fn enforce_quotas(mut req: Request<Body>, next: Next) -> Response {
+    if !is_metered(req.uri().path()) {
+        return next.run(req).await;
+    }
+    let host = request_host(req.headers());
+    let Some(organization_id) = service::organization_for_host(host.as_deref()).await else {
+        return next.run(req).await;
+    };
+    if let Err(exceeded) = service::register_request(&organization_id).await {
+        return (StatusCode::TOO_MANY_REQUESTS, exceeded.to_string()).into_response();
+    }
+    req.extensions_mut()
+        .insert(CurrentOrganization(organization_id));
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meters_only_api_outside_exemptions() {
+        assert!(is_metered("/api/a007/cross-mapping"));
+        assert!(is_metered("/api/sys/branding"));
+        assert!(!is_metered("/api/system/branding"));
+        assert!(!is_metered("/api/system/auth/login"));
+        assert!(!is_metered("/api/sys/quotas/org-1"));
+        assert!(!is_metered("/health"));
+        assert!(!is_metered("/index.html"));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod middleware;
+pub mod repository;
+pub mod service;
@@ -0,0 +1,85 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{ConnectionTrait, DatabaseBackend, EntityTrait, Set, Statement};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sys_org_quotas")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub organization_id: String,
+    pub requests_per_day: Option<i64>,
+    pub export_rows_per_month: Option<i64>,
+    pub updated_at: String,
+    pub updated_by: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+fn conn() -> &'static DatabaseConnection {
+    get_connection()
+}
+
+pub async fn list_all() -> Result<Vec<Model>, DbErr> {
+    Entity::find().all(conn()).await
+}
+
+pub async fn upsert(model: Model) -> Result<(), DbErr> {
+    let exists = Entity::find_by_id(model.organization_id.clone())
+        .one(conn())
+        .await?
+        .is_some();
+    let active = ActiveModel {
+        organization_id: Set(model.organization_id),
+        requests_per_day: Set(model.requests_per_day),
+        export_rows_per_month: Set(model.export_rows_per_month),
+        updated_at: Set(model.updated_at),
+        updated_by: Set(model.updated_by),
+    };
+    if exists {
+        active.update(conn()).await?;
+    } else {
+        active.insert(conn()).await?;
+    }
+    Ok(())
+}
+
+/// Значение счётчика за период (0, если записи нет).
+pub async fn usage_value(organization_id: &str, metric: &str, period: &str) -> Result<i64, DbErr> {
+    let row = conn()
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT value FROM sys_org_usage WHERE organization_id = ? AND metric = ? AND period = ?",
+            [organization_id.into(), metric.into(), period.into()],
+        ))
+        .await?;
+    Ok(row
+        .and_then(|r| r.try_get::<i64>("", "value").ok())
+        .unwrap_or(0))
+}
+
+/// Прибавляет к счётчику накопленное в памяти приращение.
+pub async fn add_usage(
+    organization_id: &str,
+    metric: &str,
+    period: &str,
+    delta: i64,
+) -> Result<(), DbErr> {
+    conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO sys_org_usage (organization_id, metric, period, value) VALUES (?, ?, ?, ?)
+             ON CONFLICT (organization_id, metric, period) DO UPDATE SET value = value + excluded.value",
+            [
+                organization_id.into(),
+                metric.into(),
+                period.into(),
+                delta.into(),
+            ],
+        ))
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,353 @@
+//! Квоты организаций: запросы к API в сутки и строки выгрузок в месяц.
+//!
+//! Организация запроса определяется по хосту через брендирование. Счётчики живут
+//! в памяти процесса (проверка лимита не ходит в БД на каждый запрос) и раз в
+//! несколько секунд дописываются в `sys_org_usage`.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use contracts::system::quotas::{OrgQuotaDto, OrgQuotaUpdateRequest};
+use once_cell::sync::Lazy;
+
+use super::repository;
+use crate::system::branding;
+
+pub const METRIC_REQUESTS: &str = "requests";
+pub const METRIC_EXPORT_ROWS: &str = "export_rows";
+
+/// Как часто перечитываются квоты и привязка хостов к организациям.
+const SNAPSHOT_TTL: Duration = Duration::from_secs(60);
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Организация запроса; middleware кладёт её в extensions.
+#[derive(Debug, Clone)]
+pub struct CurrentOrganization(pub String);
+
+/// Лимит организации исчерпан (HTTP 429).
+#[derive(Debug)]
+pub struct QuotaExceeded(pub String);
+
+impl std::fmt::Display for QuotaExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for QuotaExceeded {}
+
+#[derive(Default)]
+struct Snapshot {
+    branding: Vec<branding::repository::Model>,
+    quotas: HashMap<String, repository::Model>,
+    loaded_at: Option<Instant>,
+}
+
+#[derive(Default)]
+struct Counter {
+    value: i64,
+    /// Ещё не записано в БД.
+    pending: i64,
+}
+
+type CounterKey = (String, &'static str, String);
+
+static SNAPSHOT: Lazy<RwLock<Snapshot>> = Lazy::new(|| RwLock::new(Snapshot::default()));
+static COUNTERS: Lazy<Mutex<HashMap<CounterKey, Counter>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn day_period(now: DateTime<Utc>) -> String {
+    now.format("%Y-%m-%d").to_string()
+}
+
+fn month_period(now: DateTime<Utc>) -> String {
+    now.format("%Y-%m").to_string()
+}
+
+/// Превысит ли лимит `amount` сверх уже использованного `used`.
+fn exceeds(limit: Option<i64>, used: i64, amount: i64) -> bool {
+    limit.is_some_and(|limit| used + amount > limit)
+}
+
+/// Перечитывает квоты и хосты организаций (после сохранения квоты — сразу).
+pub async fn reload() -> Result<()> {
+    let branding = branding::repository::list_all().await?;
+    let quotas = repository::list_all()
+        .await?
+        .into_iter()
+        .map(|q| (q.organization_id.clone(), q))
+        .collect();
+    let mut snapshot = SNAPSHOT.write().unwrap();
+    snapshot.branding = branding;
+    snapshot.quotas = quotas;
+    snapshot.loaded_at = Some(Instant::now());
+    Ok(())
+}
+
+async fn ensure_snapshot() {
+    let stale = match SNAPSHOT.read().unwrap().loaded_at {
+        Some(at) => at.elapsed() > SNAPSHOT_TTL,
+        None => true,
+    };
+    if stale {
+        if let Err(e) = reload().await {
+            tracing::warn!("[quotas] failed to reload quotas: {e}");
+            // Не перечитываем на каждом запросе, пока БД недоступна
+            SNAPSHOT.write().unwrap().loaded_at = Some(Instant::now());
+        }
+    }
+}
+
+fn quota_for(organization_id: &str) -> Option<repository::Model> {
+    SNAPSHOT
+        .read()
+        .unwrap()
+        .quotas
+        .get(organization_id)
+        .cloned()
+}
+
+/// Организация по хосту запроса (`None` — брендирование не настроено).
+pub async fn organization_for_host(host: Option<&str>) -> Option<String> {
+    ensure_snapshot().await;
+    let snapshot = SNAPSHOT.read().unwrap();
+    branding::service::pick_for_host(&snapshot.branding, host).map(|r| r.organization_id.clone())
+}
+
+/// Текущее значение счётчика; при первом обращении за период — из БД.
+async fn counter_value(organization_id: &str, metric: &'static str, period: &str) -> i64 {
+    let key = (organization_id.to_string(), metric, period.to_string());
+    if let Some(counter) = COUNTERS.lock().unwrap().get(&key) {
+        return counter.value;
+    }
+    let stored = repository::usage_value(organization_id, metric, period)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("[quotas] failed to read usage: {e}");
+            0
+        });
+    COUNTERS
+        .lock()
+        .unwrap()
+        .entry(key)
+        .or_insert(Counter {
+            value: stored,
+            pending: 0,
+        })
+        .value
+}
+
+/// Проверяет лимит и, если он не превышен, прибавляет `amount` к счётчику.
+fn try_add(
+    organization_id: &str,
+    metric: &'static str,
+    period: &str,
+    limit: Option<i64>,
+    amount: i64,
+) -> bool {
+    let key = (organization_id.to_string(), metric, period.to_string());
+    let mut counters = COUNTERS.lock().unwrap();
+    let counter = counters.entry(key).or_default();
+    if exceeds(limit, counter.value, amount) {
+        return false;
+    }
+    counter.value += amount;
+    counter.pending += amount;
+    true
+}
+
+/// Учитывает запрос к API; при исчерпанном суточном лимите — ошибка.
+pub async fn register_request(organization_id: &str) -> Result<(), QuotaExceeded> {
+    let period = day_period(Utc::now());
+    counter_value(organization_id, METRIC_REQUESTS, &period).await;
+    let limit = quota_for(organization_id).and_then(|q| q.requests_per_day);
+    if try_add(organization_id, METRIC_REQUESTS, &period, limit, 1) {
+        Ok(())
+    } else {
+        Err(QuotaExceeded(format!(
+            "Исчерпан суточный лимит запросов организации ({}). Лимит обновится в 00:00 UTC.",
+            limit.unwrap_or_default()
+        )))
+    }
+}
+
+/// Можно ли поставить выгрузку: месячный лимит строк ещё не выбран.
+pub async fn check_export(organization_id: &str) -> Result<(), QuotaExceeded> {
+    let period = month_period(Utc::now());
+    let used = counter_value(organization_id, METRIC_EXPORT_ROWS, &period).await;
+    let limit = quota_for(organization_id).and_then(|q| q.export_rows_per_month);
+    if exceeds(limit, used, 1) {
+        return Err(QuotaExceeded(format!(
+            "Исчерпан месячный лимит строк выгрузок организации ({}).",
+            limit.unwrap_or_default()
+        )));
+    }
+    Ok(())
+}
+
+/// Учитывает строки готовой выгрузки; если они не помещаются в остаток — ошибка.
+pub async fn register_export_rows(organization_id: &str, rows: usize) -> Result<(), QuotaExceeded> {
+    let period = month_period(Utc::now());
+    let used = counter_value(organization_id, METRIC_EXPORT_ROWS, &period).await;
+    let limit = quota_for(organization_id).and_then(|q| q.export_rows_per_month);
+    if try_add(
+        organization_id,
+        METRIC_EXPORT_ROWS,
+        &period,
+        limit,
+        rows as i64,
+    ) {
+        Ok(())
+    } else {
+        Err(QuotaExceeded(format!(
+            "Выгрузка ({} строк) превышает остаток месячного лимита организации: {} из {}.",
+            rows,
+            limit.unwrap_or_default() - used,
+            limit.unwrap_or_default()
+        )))
+    }
+}
+
+/// Записывает накопленные приращения в БД и забывает счётчики прошлых периодов.
+async fn flush() {
+    let now = Utc::now();
+    let current = [day_period(now), month_period(now)];
+    let batch: Vec<(CounterKey, i64)> = {
+        let mut counters = COUNTERS.lock().unwrap();
+        let batch = counters
+            .iter_mut()
+            .filter(|(_, c)| c.pending != 0)
+            .map(|(key, c)| (key.clone(), std::mem::take(&mut c.pending)))
+            .collect();
+        counters.retain(|(_, _, period), _| current.contains(period));
+        batch
+    };
+    for ((organization_id, metric, period), delta) in batch {
+        if let Err(e) = repository::add_usage(&organization_id, metric, &period, delta).await {
+            tracing::warn!("[quotas] failed to flush usage: {e}");
+            if let Some(counter) =
+                COUNTERS
+                    .lock()
+                    .unwrap()
+                    .get_mut(&(organization_id, metric, period))
+            {
+                counter.pending += delta;
+            }
+        }
+    }
+}
+
+/// Фоновый сброс счётчиков в `sys_org_usage`.
+pub async fn run_flush_loop() {
+    loop {
+        tokio::time::sleep(FLUSH_INTERVAL).await;
+        flush().await;
+    }
+}
+
+/// Все организации: квоты и использование за текущие сутки и месяц.
+pub async fn list() -> Result<Vec<OrgQuotaDto>> {
+    flush().await;
+    let now = Utc::now();
+    let (day, month) = (day_period(now), month_period(now));
+    let quotas = repository::list_all().await?;
+    let branding = branding::repository::list_all().await?;
+    let mut items = Vec::new();
+    for (organization_id, organization_name) in branding::repository::list_organizations().await? {
+        let quota = quotas.iter().find(|q| q.organization_id == organization_id);
+        let hosts = branding
+            .iter()
+            .find(|b| b.organization_id == organization_id)
+            .map(|b| {
+                b.hosts
+                    .split(',')
+                    .map(str::to_string)
+                    .filter(|h| !h.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        items.push(OrgQuotaDto {
+            requests_today: repository::usage_value(&organization_id, METRIC_REQUESTS, &day)
+                .await?,
+            export_rows_month: repository::usage_value(
+                &organization_id,
+                METRIC_EXPORT_ROWS,
+                &month,
+            )
+            .await?,
+            requests_per_day: quota.and_then(|q| q.requests_per_day),
+            export_rows_per_month: quota.and_then(|q| q.export_rows_per_month),
+            updated_at: quota.map(|q| q.updated_at.clone()),
+            updated_by: quota.map(|q| q.updated_by.clone()),
+            hosts,
+            organization_id,
+            organization_name,
+        });
+    }
+    Ok(items)
+}
+
+pub async fn update(
+    organization_id: &str,
+    req: OrgQuotaUpdateRequest,
+    updated_by: &str,
+) -> Result<()> {
+    let organizations = branding::repository::list_organizations().await?;
+    if !organizations.iter().any(|(id, _)| id == organization_id) {
+        return Err(anyhow!("Organization not found"));
+    }
+    if [req.requests_per_day, req.export_rows_per_month]
+        .into_iter()
+        .flatten()
+        .any(|limit| limit < 0)
+    {
+        return Err(anyhow!("Invalid quota: limits must be non-negative"));
+    }
+    repository::upsert(repository::Model {
+        organization_id: organization_id.to_string(),
+        requests_per_day: req.requests_per_day,
+        export_rows_per_month: req.export_rows_per_month,
+        updated_at: Utc::now().to_rfc3339(),
+        updated_by: updated_by.to_string(),
+    })
+    .await?;
+    reload().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn limit_check() {
+        assert!(!exceeds(None, 1_000_000, 1));
+        assert!(!exceeds(Some(10), 9, 1));
+        assert!(exceeds(Some(10), 10, 1));
+        assert!(exceeds(Some(0), 0, 1));
+        assert!(exceeds(Some(100), 40, 61));
+    }
+
+    #[test]
+    fn periods_roll_over_in_utc() {
+        let evening = Utc.with_ymd_and_hms(2026, 1, 31, 23, 59, 59).unwrap();
+        let next = Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap();
+        assert_eq!(day_period(evening), "2026-01-31");
+        assert_eq!(day_period(next), "2026-02-01");
+        assert_eq!(month_period(evening), "2026-01");
+        assert_eq!(month_period(next), "2026-02");
+    }
+
+    #[test]
+    fn try_add_stops_at_limit() {
+        let org = "test-org-try-add";
+        assert!(try_add(org, METRIC_REQUESTS, "2026-01-01", Some(2), 1));
+        assert!(try_add(org, METRIC_REQUESTS, "2026-01-01", Some(2), 1));
+        assert!(!try_add(org, METRIC_REQUESTS, "2026-01-01", Some(2), 1));
+        // Другой период — свой счётчик
+        assert!(try_add(org, METRIC_REQUESTS, "2026-01-02", Some(2), 1));
+    }
+}
@@ -0,0 +1,88 @@
+//! Строки документов возврата, по которым пишутся движения решения.
+
+use anyhow::{anyhow, Result};
+use uuid::Uuid;
+
+use crate::domain::{a009_ozon_returns, a016_ym_returns, a032_wb_returns_claims};
+
+/// Документы возврата, для которых доступно решение.
+pub const RETURN_DOCUMENT_TYPES: &[(&str, &str)] = &[
+    ("a009_ozon_returns", "Возврат Ozon"),
+    ("a016_ym_returns", "Возврат Яндекс Маркет"),
+    ("a032_wb_returns_claims", "Заявка на возврат WB"),
+];
+
+pub fn is_return_document(entity_type: &str) -> bool {
+    RETURN_DOCUMENT_TYPES
+        .iter()
+        .any(|(code, _)| *code == entity_type)
+}
+
+/// Возвращённый товар — строка документа.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReturnedItem {
+    pub line_no: i32,
+    pub article: String,
+    pub product_name: String,
+    pub quantity: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReturnDocument {
+    pub organization_id: String,
+    pub items: Vec<ReturnedItem>,
+}
+
+/// Загружает строки документа возврата; `None` — документ не найден.
+pub async fn load(entity_type: &str, document_id: &str) -> Result<Option<ReturnDocument>> {
+    let id = Uuid::parse_str(document_id).map_err(|e| anyhow!("Invalid id: {}", e))?;
+    let document = match entity_type {
+        "a009_ozon_returns" => {
+            a009_ozon_returns::service::get_by_id(id)
+                .await?
+                .map(|doc| ReturnDocument {
+                    organization_id: doc.organization_id,
+                    items: vec![ReturnedItem {
+                        line_no: 1,
+                        article: doc.sku,
+                        product_name: doc.product_name,
+                        quantity: doc.quantity,
+                    }],
+                })
+        }
+        "a016_ym_returns" => {
+            a016_ym_returns::service::get_by_id(id)
+                .await?
+                .map(|doc| ReturnDocument {
+                    organization_id: doc.header.organization_id,
+                    items: doc
+                        .lines
+                        .into_iter()
+                        .enumerate()
+                        .map(|(idx, line)| ReturnedItem {
+                            line_no: idx as i32 + 1,
+                            article: line.shop_sku,
+                            product_name: line.name,
+                            quantity: line.count,
+                        })
+                        .collect(),
+                })
+        }
+        // Заявка WB — на одну единицу товара
+        "a032_wb_returns_claims" => {
+            a032_wb_returns_claims::service::get_by_id(id)
+                .await?
+                .map(|doc| ReturnDocument {
+                    organization_id: doc.organization_id,
+                    items: vec![ReturnedItem {
+                        line_no: 1,
+                        article: doc.nm_id.to_string(),
+                        product_name: doc.imt_name.unwrap_or_default(),
+                        quantity: 1,
+                    }],
+                })
+        }
+        other => return Err(anyhow!("Unsupported return document type: {}", other)),
+    };
+    Ok(document)
+}
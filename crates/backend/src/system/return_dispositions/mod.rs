@@ -0,0 +1,10 @@
+//! Решения по физическим возвратам (`/api/return-dispositions`).
+//!
+//! Черновик решения → фото (вложения документа возврата в S3) → отправка на
+//! утверждение → утверждение пользователем со scope `approve_return_disposition`
+//! (или отклонение с причиной). Утверждение записывает движения по строкам
+//! документа в `p917_return_stock_ledger` с тегом решения.
+
+pub mod items;
+pub mod repository;
+pub mod service;
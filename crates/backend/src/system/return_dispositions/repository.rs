@@ -0,0 +1,141 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, Set, Statement};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sys_return_dispositions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub entity_type: String,
+    pub document_id: String,
+    pub decision: String,
+    pub status: String,
+    pub comment: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+    pub submitted_at: Option<String>,
+    pub decided_by: Option<String>,
+    pub decided_at: Option<String>,
+    pub reject_reason: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+fn conn() -> &'static DatabaseConnection {
+    get_connection()
+}
+
+pub async fn insert(model: Model) -> Result<(), DbErr> {
+    ActiveModel {
+        id: Set(model.id),
+        entity_type: Set(model.entity_type),
+        document_id: Set(model.document_id),
+        decision: Set(model.decision),
+        status: Set(model.status),
+        comment: Set(model.comment),
+        created_by: Set(model.created_by),
+        created_at: Set(model.created_at),
+        submitted_at: Set(model.submitted_at),
+        decided_by: Set(model.decided_by),
+        decided_at: Set(model.decided_at),
+        reject_reason: Set(model.reject_reason),
+    }
+    .insert(conn())
+    .await?;
+    Ok(())
+}
+
+pub async fn get_by_id(id: &str) -> Result<Option<Model>, DbErr> {
+    Entity::find_by_id(id.to_string()).one(conn()).await
+}
+
+pub async fn find_for_document(
+    entity_type: &str,
+    document_id: &str,
+) -> Result<Option<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::EntityType.eq(entity_type))
+        .filter(Column::DocumentId.eq(document_id))
+        .one(conn())
+        .await
+}
+
+/// Новое решение и комментарий; черновик снова становится черновиком (после
+/// отклонения — тоже). `false` — решение уже на утверждении или утверждено.
+pub async fn update_draft(
+    id: &str,
+    decision: &str,
+    comment: Option<String>,
+    editable_statuses: &[&str],
+    draft_status: &str,
+) -> Result<bool, DbErr> {
+    let placeholders = vec!["?"; editable_statuses.len()].join(", ");
+    let mut values: Vec<sea_orm::Value> = vec![
+        decision.into(),
+        comment.into(),
+        draft_status.into(),
+        id.into(),
+    ];
+    values.extend(editable_statuses.iter().map(|s| (*s).into()));
+    let result = conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            format!(
+                "UPDATE sys_return_dispositions \
+                 SET decision = ?, comment = ?, status = ?, submitted_at = NULL, \
+                     decided_by = NULL, decided_at = NULL, reject_reason = NULL \
+                 WHERE id = ? AND status IN ({})",
+                placeholders
+            ),
+            values,
+        ))
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Отправка на утверждение: `from` → `to`; `false` — решение уже не в статусе `from`.
+pub async fn submit(id: &str, from: &str, to: &str, submitted_at: &str) -> Result<bool, DbErr> {
+    let result = conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_return_dispositions SET status = ?1, submitted_at = ?2 \
+             WHERE id = ?3 AND status = ?4",
+            vec![to.into(), submitted_at.into(), id.into(), from.into()],
+        ))
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Утверждение или отклонение: `from` → `to`; `false` — решение уже не в статусе `from`.
+pub async fn decide<C: ConnectionTrait>(
+    db: &C,
+    id: &str,
+    from: &str,
+    to: &str,
+    decided_by: &str,
+    decided_at: &str,
+    reject_reason: Option<String>,
+) -> Result<bool, DbErr> {
+    let result = db
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_return_dispositions \
+             SET status = ?1, decided_by = ?2, decided_at = ?3, reject_reason = ?4 \
+             WHERE id = ?5 AND status = ?6",
+            vec![
+                to.into(),
+                decided_by.into(),
+                decided_at.into(),
+                reject_reason.into(),
+                id.into(),
+                from.into(),
+            ],
+        ))
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
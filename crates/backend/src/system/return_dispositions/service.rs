@@ -0,0 +1,436 @@
+use chrono::Utc;
+use contracts::projections::p917_return_stock_ledger::dto::ReturnStockLedgerEntryDto;
+use contracts::system::return_dispositions::{
+    ReturnDecision, ReturnDispositionDto, ReturnDispositionStatus, ReturnDispositionView,
+    SaveReturnDispositionRequest,
+};
+use contracts::system::s3::{S3FileCategory, S3FileDto};
+use sea_orm::TransactionTrait;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::items::{self, ReturnDocument};
+use super::repository;
+use crate::projections::p917_return_stock_ledger::repository as ledger;
+use crate::shared::data::db::get_connection;
+use crate::system::s3::ocr::{file_kind, FileKind};
+use crate::system::s3::service::{self as s3, UploadedFile};
+
+/// Сколько фото нужно для отправки на утверждение.
+pub const MIN_PHOTOS: usize = 1;
+
+/// Максимум символов комментария и причины отклонения.
+const MAX_TEXT_LEN: usize = 1000;
+
+/// Ошибка перехода; тексты `Invalid`/`Conflict` — для пользователя.
+#[derive(Debug, Error)]
+pub enum DispositionError {
+    #[error("{0}")]
+    Invalid(String),
+    #[error("Решение не найдено")]
+    NotFound,
+    #[error("{0}")]
+    Conflict(String),
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl From<sea_orm::DbErr> for DispositionError {
+    fn from(e: sea_orm::DbErr) -> Self {
+        Self::Internal(e.into())
+    }
+}
+
+type Result<T> = std::result::Result<T, DispositionError>;
+
+fn status_of(model: &repository::Model) -> ReturnDispositionStatus {
+    ReturnDispositionStatus::from_code(&model.status).unwrap_or(ReturnDispositionStatus::Draft)
+}
+
+fn to_dto(model: repository::Model) -> ReturnDispositionDto {
+    ReturnDispositionDto {
+        decision: ReturnDecision::from_code(&model.decision)
+            .unwrap_or(ReturnDecision::ReturnToStock),
+        status: status_of(&model),
+        id: model.id,
+        entity_type: model.entity_type,
+        document_id: model.document_id,
+        comment: model.comment,
+        created_by: model.created_by,
+        created_at: model.created_at,
+        submitted_at: model.submitted_at,
+        decided_by: model.decided_by,
+        decided_at: model.decided_at,
+        reject_reason: model.reject_reason,
+    }
+}
+
+fn clean_text(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| v.chars().take(MAX_TEXT_LEN).collect())
+}
+
+/// Проверка документа запроса (тип и id).
+pub fn validate_document(entity_type: &str, document_id: &str) -> Result<()> {
+    if !items::is_return_document(entity_type) {
+        return Err(DispositionError::Invalid(format!(
+            "Решение по возврату не поддерживается для '{}'",
+            entity_type
+        )));
+    }
+    if Uuid::parse_str(document_id).is_err() {
+        return Err(DispositionError::Invalid(
+            "Некорректный id документа".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Фото решения — вложения-изображения документа возврата.
+pub async fn photos(entity_type: &str, document_id: &str) -> Result<Vec<S3FileDto>> {
+    Ok(s3::list_for_document(entity_type, document_id)
+        .await?
+        .into_iter()
+        .filter(|file| {
+            file_kind(file.content_type.as_deref(), &file.original_filename) == FileKind::Image
+        })
+        .collect())
+}
+
+/// Состояние решения для карточки документа.
+pub async fn get_view(
+    entity_type: &str,
+    document_id: &str,
+    can_approve: bool,
+) -> Result<ReturnDispositionView> {
+    let disposition = repository::find_for_document(entity_type, document_id).await?;
+    let ledger = match &disposition {
+        Some(model) if status_of(model) == ReturnDispositionStatus::Approved => {
+            ledger::list_by_registrator(&model.id)
+                .await?
+                .into_iter()
+                .map(ReturnStockLedgerEntryDto::from)
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+    Ok(ReturnDispositionView {
+        disposition: disposition.map(to_dto),
+        photos: photos(entity_type, document_id).await?,
+        ledger,
+        can_approve,
+    })
+}
+
+pub async fn get(id: &str) -> Result<ReturnDispositionDto> {
+    repository::get_by_id(id)
+        .await?
+        .map(to_dto)
+        .ok_or(DispositionError::NotFound)
+}
+
+/// Создаёт решение по документу или переписывает черновик/отклонённое (оно снова
+/// становится черновиком). Запрос должен пройти [`validate_document`].
+pub async fn save(
+    request: &SaveReturnDispositionRequest,
+    username: &str,
+) -> Result<ReturnDispositionDto> {
+    if items::load(&request.entity_type, &request.document_id)
+        .await?
+        .is_none()
+    {
+        return Err(DispositionError::Invalid(
+            "Документ возврата не найден".to_string(),
+        ));
+    }
+    let comment = clean_text(request.comment.as_deref());
+
+    let Some(existing) =
+        repository::find_for_document(&request.entity_type, &request.document_id).await?
+    else {
+        let model = repository::Model {
+            id: Uuid::new_v4().to_string(),
+            entity_type: request.entity_type.clone(),
+            document_id: request.document_id.clone(),
+            decision: request.decision.code().to_string(),
+            status: ReturnDispositionStatus::Draft.code().to_string(),
+            comment,
+            created_by: username.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            submitted_at: None,
+            decided_by: None,
+            decided_at: None,
+            reject_reason: None,
+        };
+        repository::insert(model.clone()).await?;
+        return Ok(to_dto(model));
+    };
+
+    let editable: Vec<&str> = ReturnDispositionStatus::ALL
+        .into_iter()
+        .filter(|s| s.is_editable())
+        .map(|s| s.code())
+        .collect();
+    let updated = repository::update_draft(
+        &existing.id,
+        request.decision.code(),
+        comment,
+        &editable,
+        ReturnDispositionStatus::Draft.code(),
+    )
+    .await?;
+    if !updated {
+        return Err(DispositionError::Conflict(
+            "Решение уже на утверждении или утверждено".to_string(),
+        ));
+    }
+    get(&existing.id).await
+}
+
+/// Загружает фото и привязывает его к документу возврата. Пока решение на
+/// утверждении или утверждено, фото не добавляются.
+pub async fn add_photo(
+    entity_type: &str,
+    document_id: &str,
+    file: UploadedFile,
+    user_id: &str,
+) -> Result<S3FileDto> {
+    if file_kind(file.content_type.as_deref(), &file.filename) != FileKind::Image {
+        return Err(DispositionError::Invalid(
+            "Фото должно быть изображением (JPEG, PNG, ...)".to_string(),
+        ));
+    }
+    if let Some(existing) = repository::find_for_document(entity_type, document_id).await? {
+        if !status_of(&existing).is_editable() {
+            return Err(DispositionError::Conflict(
+                "Решение уже на утверждении или утверждено".to_string(),
+            ));
+        }
+    }
+
+    let mut stored = s3::upload(S3FileCategory::Other, file, Some(user_id.to_string())).await?;
+    s3::attach_to_document(&stored.id, Some(entity_type), Some(document_id)).await?;
+    stored.document_type = Some(entity_type.to_string());
+    stored.document_id = Some(document_id.to_string());
+    Ok(stored)
+}
+
+/// Отправка черновика на утверждение; нужно не меньше [`MIN_PHOTOS`] фото.
+pub async fn submit(id: &str) -> Result<ReturnDispositionDto> {
+    let model = repository::get_by_id(id)
+        .await?
+        .ok_or(DispositionError::NotFound)?;
+    let photo_count = photos(&model.entity_type, &model.document_id).await?.len();
+    if photo_count < MIN_PHOTOS {
+        return Err(DispositionError::Invalid(format!(
+            "Приложите фото возврата (нужно не меньше {})",
+            MIN_PHOTOS
+        )));
+    }
+
+    let submitted = repository::submit(
+        id,
+        ReturnDispositionStatus::Draft.code(),
+        ReturnDispositionStatus::PendingApproval.code(),
+        &Utc::now().to_rfc3339(),
+    )
+    .await?;
+    if !submitted {
+        return Err(DispositionError::Conflict(
+            "Отправить можно только черновик".to_string(),
+        ));
+    }
+    get(id).await
+}
+
+/// Движения по строкам документа с тегом решения.
+pub fn build_ledger_entries(
+    model: &repository::Model,
+    decision: ReturnDecision,
+    document: &ReturnDocument,
+    entry_date: &str,
+    created_at: &str,
+) -> Vec<ledger::Model> {
+    document
+        .items
+        .iter()
+        .filter(|item| item.quantity > 0)
+        .map(|item| ledger::Model {
+            id: Uuid::new_v4().to_string(),
+            entry_date: entry_date.to_string(),
+            decision: decision.code().to_string(),
+            entity_type: model.entity_type.clone(),
+            document_id: model.document_id.clone(),
+            line_no: item.line_no,
+            organization_id: document.organization_id.clone(),
+            article: item.article.clone(),
+            product_name: item.product_name.clone(),
+            quantity: item.quantity,
+            stock_delta: decision.stock_delta(item.quantity),
+            registrator_ref: model.id.clone(),
+            created_at: created_at.to_string(),
+        })
+        .collect()
+}
+
+/// Утверждение: статус и движения `p917_return_stock_ledger` — в одной транзакции.
+pub async fn approve(id: &str, username: &str) -> Result<ReturnDispositionDto> {
+    let model = repository::get_by_id(id)
+        .await?
+        .ok_or(DispositionError::NotFound)?;
+    let decision = ReturnDecision::from_code(&model.decision).ok_or_else(|| {
+        DispositionError::Internal(anyhow::anyhow!("Unknown decision '{}'", model.decision))
+    })?;
+    let document = items::load(&model.entity_type, &model.document_id)
+        .await?
+        .ok_or_else(|| DispositionError::Conflict("Документ возврата удалён".to_string()))?;
+
+    let now = Utc::now();
+    let decided_at = now.to_rfc3339();
+    let entries = build_ledger_entries(
+        &model,
+        decision,
+        &document,
+        &now.format("%Y-%m-%d").to_string(),
+        &decided_at,
+    );
+
+    let txn = get_connection().begin().await?;
+    let approved = repository::decide(
+        &txn,
+        id,
+        ReturnDispositionStatus::PendingApproval.code(),
+        ReturnDispositionStatus::Approved.code(),
+        username,
+        &decided_at,
+        None,
+    )
+    .await?;
+    if !approved {
+        txn.rollback().await?;
+        return Err(DispositionError::Conflict(
+            "Утвердить можно только решение на утверждении".to_string(),
+        ));
+    }
+    ledger::replace_for_registrator_with_conn(&txn, id, entries).await?;
+    txn.commit().await?;
+    get(id).await
+}
+
+/// Отклонение с обязательной причиной; решение можно исправить и отправить снова.
+pub async fn reject(id: &str, username: &str, reason: &str) -> Result<ReturnDispositionDto> {
+    let Some(reason) = clean_text(Some(reason)) else {
+        return Err(DispositionError::Invalid(
+            "Укажите причину отклонения".to_string(),
+        ));
+    };
+    let rejected = repository::decide(
+        get_connection(),
+        id,
+        ReturnDispositionStatus::PendingApproval.code(),
+        ReturnDispositionStatus::Rejected.code(),
+        username,
+        &Utc::now().to_rfc3339(),
+        Some(reason),
+    )
+    .await?;
+    if !rejected {
+        return Err(match repository::get_by_id(id).await? {
+            None => DispositionError::NotFound,
+            Some(_) => DispositionError::Conflict(
+                "Отклонить можно только решение на утверждении".to_string(),
+            ),
+        });
+    }
+    get(id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::return_dispositions::items::ReturnedItem;
+
+    fn disposition() -> repository::Model {
+        repository::Model {
+            id: "d1".to_string(),
+            entity_type: "a016_ym_returns".to_string(),
+            document_id: "5f0c6a8e-2f5b-4b7e-9a43-0d7f7c1c2b11".to_string(),
+            decision: "dispose".to_string(),
+            status: "pending_approval".to_string(),
+            comment: None,
+            created_by: "stock".to_string(),
+            created_at: "2026-10-17T10:00:00Z".to_string(),
+            submitted_at: None,
+            decided_by: None,
+            decided_at: None,
+            reject_reason: None,
+        }
+    }
+
+    fn document() -> ReturnDocument {
+        let item = |line_no, quantity| ReturnedItem {
+            line_no,
+            article: format!("SKU-{}", line_no),
+            product_name: "Чехол".to_string(),
+            quantity,
+        };
+        ReturnDocument {
+            organization_id: "org".to_string(),
+            items: vec![item(1, 2), item(2, 0), item(3, 1)],
+        }
+    }
+
+    #[test]
+    fn ledger_entries_are_tagged_and_move_stock_only_on_return() {
+        let model = disposition();
+        let entries = build_ledger_entries(
+            &model,
+            ReturnDecision::ReturnToStock,
+            &document(),
+            "2026-10-17",
+            "2026-10-17T12:00:00Z",
+        );
+        assert_eq!(
+            entries.iter().map(|e| e.line_no).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert!(entries.iter().all(|e| e.decision == "return_to_stock"));
+        assert_eq!(entries[0].stock_delta, 2);
+        assert_eq!(entries[0].registrator_ref, "d1");
+
+        let disposed = build_ledger_entries(
+            &model,
+            ReturnDecision::Dispose,
+            &document(),
+            "2026-10-17",
+            "2026-10-17T12:00:00Z",
+        );
+        assert!(disposed
+            .iter()
+            .all(|e| e.stock_delta == 0 && e.quantity > 0));
+    }
+
+    #[test]
+    fn validate_document_accepts_only_return_documents() {
+        let id = "5f0c6a8e-2f5b-4b7e-9a43-0d7f7c1c2b11";
+        assert!(validate_document("a009_ozon_returns", id).is_ok());
+        assert!(validate_document("a032_wb_returns_claims", id).is_ok());
+        assert!(matches!(
+            validate_document("a012_wb_sales", id),
+            Err(DispositionError::Invalid(_))
+        ));
+        assert!(validate_document("a016_ym_returns", "42").is_err());
+    }
+
+    #[test]
+    fn clean_text_trims_and_drops_empty() {
+        assert_eq!(
+            clean_text(Some("  брак упаковки ")).as_deref(),
+            Some("брак упаковки")
+        );
+        assert_eq!(clean_text(Some("   ")), None);
+        assert_eq!(clean_text(None), None);
+    }
+}
@@ -192,3 +192,57 @@ pub async fn replace_all_scope_access(role_id: &str, grants: &[RoleScopeAccess])
         .context("Failed to commit scope access transaction")?;
     Ok(())
 }
+
+/// Ids of additional roles assigned to a user (sys_user_roles).
+pub async fn get_user_role_ids(user_id: &str) -> Result<Vec<String>> {
+    use crate::shared::data::db::get_connection;
+
+    let conn = get_connection();
+
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT role_id FROM sys_user_roles WHERE user_id = ? ORDER BY role_id ASC",
+            [user_id.into()],
+        ))
+        .await?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row.try_get::<i64>("", "role_id")?.to_string());
+    }
+
+    Ok(result)
+}
+
+/// Replace all additional roles of a user atomically (DELETE + INSERT in a transaction).
+pub async fn replace_user_roles(user_id: &str, role_ids: &[String]) -> Result<()> {
+    use crate::shared::data::db::get_connection;
+
+    let conn = get_connection();
+    let txn = conn.begin().await?;
+
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM sys_user_roles WHERE user_id = ?",
+        [user_id.into()],
+    ))
+    .await
+    .context("Failed to clear user roles")?;
+
+    for role_id in role_ids {
+        let role_i64: i64 = role_id.parse().unwrap_or(0);
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO sys_user_roles (user_id, role_id) VALUES (?, ?)",
+            [user_id.into(), role_i64.into()],
+        ))
+        .await
+        .context("Failed to insert user role")?;
+    }
+
+    txn.commit()
+        .await
+        .context("Failed to commit user roles transaction")?;
+    Ok(())
+}
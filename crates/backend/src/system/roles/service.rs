@@ -1,8 +1,10 @@
 use anyhow::Result;
-use contracts::system::roles::{CreateRoleDto, Role, RoleScopeAccess, UpdateRoleDto};
+use contracts::system::roles::{
+    CreateRoleDto, Role, RolePreview, RoleScopeAccess, UpdateRoleDto, UserRolesDto,
+};
 
 use super::repository;
-use crate::system::access::primary_roles;
+use crate::system::access::{primary_roles, resolver};
 
 pub async fn list_all() -> Result<Vec<Role>> {
     repository::list_all().await
@@ -78,6 +80,40 @@ pub async fn get_permissions(role_id: &str) -> Result<Vec<RoleScopeAccess>> {
     repository::get_scope_access(role_id).await
 }
 
+/// Effective scopes of a role for the admin "view as role" preview (read-only).
+/// System roles are previewed as a primary role, custom roles on top of `viewer`
+/// (the primary role new users get by default).
+pub async fn preview(role_id: &str) -> Result<RolePreview> {
+    let role = repository::get_by_id(role_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Role not found"))?;
+
+    if role.code == "admin" {
+        return Err(anyhow::anyhow!(
+            "Cannot preview the admin role — it bypasses all checks"
+        ));
+    }
+
+    let (primary_role, additional) = if role.is_system {
+        (role.code.clone(), Vec::new())
+    } else {
+        let grants = repository::get_scope_access(role_id)
+            .await?
+            .into_iter()
+            .map(|g| (g.scope_id, g.access_mode))
+            .collect();
+        ("viewer".to_string(), grants)
+    };
+
+    Ok(RolePreview {
+        scopes: resolver::merge_scopes(&primary_role, &additional),
+        role_id: role.id,
+        role_code: role.code,
+        role_name: role.name,
+        primary_role,
+    })
+}
+
 /// Replace all custom scope grants for a role.
 /// System roles cannot be edited via this API.
 pub async fn update_permissions(role_id: &str, grants: Vec<RoleScopeAccess>) -> Result<()> {
@@ -104,3 +140,41 @@ pub async fn update_permissions(role_id: &str, grants: Vec<RoleScopeAccess>) ->
 
     repository::replace_all_scope_access(role_id, &grants).await
 }
+
+/// Additional roles of a user. The primary role lives in `sys_users.primary_role_code`
+/// and is not part of this list.
+pub async fn get_user_roles(user_id: &str) -> Result<UserRolesDto> {
+    crate::system::users::service::get_by_id(user_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+
+    Ok(UserRolesDto {
+        role_ids: repository::get_user_role_ids(user_id).await?,
+    })
+}
+
+/// Replace the set of additional roles of a user.
+/// Only custom roles can be assigned — system roles are chosen as the primary role.
+pub async fn update_user_roles(user_id: &str, dto: UserRolesDto) -> Result<()> {
+    crate::system::users::service::get_by_id(user_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+
+    let mut role_ids = dto.role_ids;
+    role_ids.sort();
+    role_ids.dedup();
+
+    for role_id in &role_ids {
+        let role = repository::get_by_id(role_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Role not found: {}", role_id))?;
+        if role.is_system {
+            return Err(anyhow::anyhow!(
+                "Cannot assign system role '{}' as additional — use primary_role_code",
+                role.code
+            ));
+        }
+    }
+
+    repository::replace_user_roles(user_id, &role_ids).await
+}
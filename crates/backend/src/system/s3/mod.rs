@@ -1,3 +1,5 @@
 pub mod client;
+pub mod ocr;
 pub mod repository;
 pub mod service;
+pub mod text_index;
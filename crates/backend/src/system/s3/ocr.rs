@@ -0,0 +1,289 @@
+//! Распознавание текста вложений S3 для полнотекстового поиска (task030).
+//!
+//! Обрабатываются файлы категории «Документы» (сканы актов, счета). Движки подключаются
+//! через [`OcrEngine`]: текстовые файлы читаются как есть, изображения и PDF — внешними
+//! утилитами, если `[ocr].engine = "tesseract"`. Новый движок — ещё одна реализация
+//! трейта в [`engines`].
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use uuid::Uuid;
+
+use super::{service, text_index};
+use crate::shared::config::{self, OcrConfig};
+
+/// Сколько раз повторять распознавание файла, завершившегося ошибкой.
+pub const MAX_ATTEMPTS: i64 = 3;
+
+/// Вид содержимого файла по MIME-типу и расширению.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Text,
+    Image,
+    Pdf,
+    Unsupported,
+}
+
+pub fn file_kind(content_type: Option<&str>, filename: &str) -> FileKind {
+    let content_type = content_type.unwrap_or_default().to_ascii_lowercase();
+    let extension = filename
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    if content_type == "application/pdf" || extension == "pdf" {
+        FileKind::Pdf
+    } else if content_type.starts_with("image/")
+        || matches!(
+            extension.as_str(),
+            "png" | "jpg" | "jpeg" | "tif" | "tiff" | "bmp" | "webp"
+        )
+    {
+        FileKind::Image
+    } else if content_type.starts_with("text/")
+        || matches!(extension.as_str(), "txt" | "csv" | "md" | "xml" | "json")
+    {
+        FileKind::Text
+    } else {
+        FileKind::Unsupported
+    }
+}
+
+/// Движок извлечения текста.
+#[async_trait]
+pub trait OcrEngine: Send + Sync {
+    /// Код движка, сохраняется в `sys_file_ocr.engine`
+    fn code(&self) -> &'static str;
+    fn supports(&self, kind: FileKind) -> bool;
+    async fn extract(&self, kind: FileKind, bytes: Bytes) -> Result<String>;
+}
+
+/// Текстовые файлы: UTF-8 (с заменой битых последовательностей).
+struct PlainTextEngine;
+
+#[async_trait]
+impl OcrEngine for PlainTextEngine {
+    fn code(&self) -> &'static str {
+        "text"
+    }
+
+    fn supports(&self, kind: FileKind) -> bool {
+        kind == FileKind::Text
+    }
+
+    async fn extract(&self, _kind: FileKind, bytes: Bytes) -> Result<String> {
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+/// Сканы: `tesseract <файл> stdout -l <языки>`.
+struct TesseractEngine {
+    command: String,
+    languages: String,
+}
+
+#[async_trait]
+impl OcrEngine for TesseractEngine {
+    fn code(&self) -> &'static str {
+        "tesseract"
+    }
+
+    fn supports(&self, kind: FileKind) -> bool {
+        kind == FileKind::Image
+    }
+
+    async fn extract(&self, _kind: FileKind, bytes: Bytes) -> Result<String> {
+        let languages = self.languages.clone();
+        run_with_temp_file(&self.command, "img", bytes, move |path| {
+            vec![
+                path,
+                "stdout".to_string(),
+                "-l".to_string(),
+                languages.clone(),
+            ]
+        })
+        .await
+    }
+}
+
+/// PDF: текстовый слой через `pdftotext <файл> -`. Сканы без текстового слоя
+/// дают пустой текст.
+struct PdfTextEngine {
+    command: String,
+}
+
+#[async_trait]
+impl OcrEngine for PdfTextEngine {
+    fn code(&self) -> &'static str {
+        "pdftotext"
+    }
+
+    fn supports(&self, kind: FileKind) -> bool {
+        kind == FileKind::Pdf
+    }
+
+    async fn extract(&self, _kind: FileKind, bytes: Bytes) -> Result<String> {
+        run_with_temp_file(&self.command, "pdf", bytes, |path| {
+            vec![path, "-".to_string()]
+        })
+        .await
+    }
+}
+
+/// Пишет содержимое во временный файл, запускает утилиту и возвращает её stdout.
+async fn run_with_temp_file(
+    command: &str,
+    extension: &str,
+    bytes: Bytes,
+    args: impl Fn(String) -> Vec<String> + Send + 'static,
+) -> Result<String> {
+    let path = std::env::temp_dir().join(format!("ocr-{}.{}", Uuid::new_v4(), extension));
+    let command = command.to_string();
+
+    tokio::task::spawn_blocking(move || {
+        std::fs::write(&path, &bytes).context("failed to write temp file")?;
+        let output = std::process::Command::new(&command)
+            .args(args(path.to_string_lossy().into_owned()))
+            .output();
+        let _ = std::fs::remove_file(&path);
+
+        let output = output.with_context(|| format!("failed to run '{}'", command))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!(
+                "'{}' exited with {}: {}",
+                command,
+                output.status,
+                stderr.trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    })
+    .await
+    .map_err(|e| anyhow!("OCR worker panicked: {}", e))?
+}
+
+/// Движки по настройке `[ocr].engine`; текстовые файлы обрабатываются всегда.
+pub fn engines(cfg: &OcrConfig) -> Result<Vec<Box<dyn OcrEngine>>> {
+    let mut engines: Vec<Box<dyn OcrEngine>> = vec![Box::new(PlainTextEngine)];
+    match cfg.engine.trim() {
+        "" | "none" => {}
+        "tesseract" => {
+            engines.push(Box::new(TesseractEngine {
+                command: cfg.tesseract_command.clone(),
+                languages: cfg.languages.clone(),
+            }));
+            engines.push(Box::new(PdfTextEngine {
+                command: cfg.pdftotext_command.clone(),
+            }));
+        }
+        other => bail!("Unknown [ocr].engine '{}'", other),
+    }
+    Ok(engines)
+}
+
+/// Схлопывает пробелы и переводы строк: в индекс уходит одна строка текста.
+pub fn normalize_text(raw: &str) -> String {
+    raw.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Итог запуска распознавания.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OcrRunReport {
+    pub processed: usize,
+    pub indexed: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub errors: Vec<String>,
+}
+
+/// Распознаёт до `batch_size` файлов без результата (и, при `retry_failed`, упавших
+/// ранее меньше [`MAX_ATTEMPTS`] раз).
+pub async fn process_pending(batch_size: u64, retry_failed: bool) -> Result<OcrRunReport> {
+    let cfg = config::load_config()?.ocr;
+    let engines = engines(&cfg)?;
+    let pending = text_index::pending(batch_size, retry_failed).await?;
+
+    let mut report = OcrRunReport::default();
+    for file in pending {
+        report.processed += 1;
+        let kind = file_kind(file.content_type.as_deref(), &file.original_filename);
+        let Some(engine) = engines.iter().find(|engine| engine.supports(kind)) else {
+            text_index::save_skipped(&file.id, "unsupported file type").await?;
+            report.skipped += 1;
+            continue;
+        };
+        if file.size_bytes.max(0) as u64 > cfg.max_file_bytes() {
+            text_index::save_skipped(&file.id, "file exceeds [ocr].max_file_mb").await?;
+            report.skipped += 1;
+            continue;
+        }
+
+        let result = match service::download(&file.id).await {
+            Ok(Some(download)) => engine.extract(kind, download.bytes).await,
+            Ok(None) => Err(anyhow!("file was deleted")),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(text) => {
+                text_index::save_text(&file.id, engine.code(), &normalize_text(&text)).await?;
+                report.indexed += 1;
+            }
+            Err(e) => {
+                let message = e.to_string();
+                text_index::save_failed(&file.id, engine.code(), &message).await?;
+                report
+                    .errors
+                    .push(format!("{}: {}", file.original_filename, message));
+                report.failed += 1;
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_kind_uses_content_type_then_extension() {
+        assert_eq!(file_kind(Some("application/pdf"), "act"), FileKind::Pdf);
+        assert_eq!(file_kind(None, "Акт 12.PDF"), FileKind::Pdf);
+        assert_eq!(file_kind(Some("image/jpeg"), "scan"), FileKind::Image);
+        assert_eq!(file_kind(None, "scan.tiff"), FileKind::Image);
+        assert_eq!(file_kind(Some("text/plain"), "invoice"), FileKind::Text);
+        assert_eq!(
+            file_kind(Some("application/zip"), "a.zip"),
+            FileKind::Unsupported
+        );
+    }
+
+    #[test]
+    fn engine_set_depends_on_config() {
+        let mut cfg = OcrConfig::default();
+        let codes = |cfg: &OcrConfig| {
+            engines(cfg)
+                .unwrap()
+                .iter()
+                .map(|e| e.code())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(codes(&cfg), vec!["text"]);
+
+        cfg.engine = "tesseract".to_string();
+        assert_eq!(codes(&cfg), vec!["text", "tesseract", "pdftotext"]);
+
+        cfg.engine = "cloud".to_string();
+        assert!(engines(&cfg).is_err());
+    }
+
+    #[test]
+    fn normalize_text_collapses_whitespace() {
+        assert_eq!(
+            normalize_text("  Акт №12\n\nот  01.02.2026\t"),
+            "Акт №12 от 01.02.2026"
+        );
+    }
+}
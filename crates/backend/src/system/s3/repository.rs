@@ -18,6 +18,8 @@ fn row_to_file(row: &sea_orm::QueryResult) -> Result<S3FileDto> {
         uploaded_by_user_id: row.try_get("", "uploaded_by_user_id")?,
         created_at: row.try_get("", "created_at")?,
         updated_at: row.try_get("", "updated_at")?,
+        document_type: row.try_get("", "document_type")?,
+        document_id: row.try_get("", "document_id")?,
     })
 }
 
@@ -27,8 +29,9 @@ pub async fn insert(file: &S3FileDto) -> Result<()> {
         DatabaseBackend::Sqlite,
         "INSERT INTO sys_files_s3 (
             id, category, bucket, object_key, original_filename, content_type, size_bytes,
-            etag, uploaded_by_user_id, created_at, updated_at, is_deleted
-         ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0)",
+            etag, uploaded_by_user_id, created_at, updated_at, document_type, document_id,
+            is_deleted
+         ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0)",
         [
             file.id.clone().into(),
             file.category.as_str().to_string().into(),
@@ -41,6 +44,8 @@ pub async fn insert(file: &S3FileDto) -> Result<()> {
             file.uploaded_by_user_id.clone().into(),
             file.created_at.clone().into(),
             file.updated_at.clone().into(),
+            file.document_type.clone().into(),
+            file.document_id.clone().into(),
         ],
     ))
     .await?;
@@ -53,7 +58,7 @@ pub async fn list(category: Option<S3FileCategory>) -> Result<Vec<S3FileDto>> {
         conn.query_all(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
             "SELECT id, category, bucket, object_key, original_filename, content_type, size_bytes,
-                    etag, uploaded_by_user_id, created_at, updated_at
+                    etag, uploaded_by_user_id, created_at, updated_at, document_type, document_id
              FROM sys_files_s3
              WHERE is_deleted = 0 AND category = ?
              ORDER BY created_at DESC",
@@ -64,7 +69,7 @@ pub async fn list(category: Option<S3FileCategory>) -> Result<Vec<S3FileDto>> {
         conn.query_all(Statement::from_string(
             DatabaseBackend::Sqlite,
             "SELECT id, category, bucket, object_key, original_filename, content_type, size_bytes,
-                    etag, uploaded_by_user_id, created_at, updated_at
+                    etag, uploaded_by_user_id, created_at, updated_at, document_type, document_id
              FROM sys_files_s3
              WHERE is_deleted = 0
              ORDER BY created_at DESC"
@@ -82,7 +87,7 @@ pub async fn get_active_by_id(id: &str) -> Result<Option<S3FileDto>> {
         .query_one(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
             "SELECT id, category, bucket, object_key, original_filename, content_type, size_bytes,
-                    etag, uploaded_by_user_id, created_at, updated_at
+                    etag, uploaded_by_user_id, created_at, updated_at, document_type, document_id
              FROM sys_files_s3
              WHERE id = ? AND is_deleted = 0",
             [id.into()],
@@ -92,6 +97,24 @@ pub async fn get_active_by_id(id: &str) -> Result<Option<S3FileDto>> {
     row.as_ref().map(row_to_file).transpose()
 }
 
+/// Файлы, привязанные к документу, в порядке загрузки.
+pub async fn list_for_document(document_type: &str, document_id: &str) -> Result<Vec<S3FileDto>> {
+    let conn = get_connection();
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT id, category, bucket, object_key, original_filename, content_type, size_bytes,
+                    etag, uploaded_by_user_id, created_at, updated_at, document_type, document_id
+             FROM sys_files_s3
+             WHERE is_deleted = 0 AND document_type = ? AND document_id = ?
+             ORDER BY created_at",
+            [document_type.into(), document_id.into()],
+        ))
+        .await?;
+
+    rows.iter().map(row_to_file).collect()
+}
+
 pub async fn soft_delete(id: &str, deleted_at: &str) -> Result<bool> {
     let conn = get_connection();
     let result = conn
@@ -110,3 +133,28 @@ pub async fn soft_delete(id: &str, deleted_at: &str) -> Result<bool> {
 
     Ok(result.rows_affected() > 0)
 }
+
+pub async fn set_document(
+    id: &str,
+    document_type: Option<&str>,
+    document_id: Option<&str>,
+    updated_at: &str,
+) -> Result<bool> {
+    let conn = get_connection();
+    let result = conn
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_files_s3
+             SET document_type = ?, document_id = ?, updated_at = ?
+             WHERE id = ? AND is_deleted = 0",
+            [
+                document_type.map(str::to_string).into(),
+                document_id.map(str::to_string).into(),
+                updated_at.to_string().into(),
+                id.into(),
+            ],
+        ))
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
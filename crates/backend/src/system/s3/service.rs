@@ -4,7 +4,7 @@ use chrono::{Datelike, Utc};
 use contracts::system::s3::{S3FileCategory, S3FileDto};
 use uuid::Uuid;
 
-use super::{client, repository};
+use super::{client, repository, text_index};
 use crate::shared::config;
 
 pub struct UploadedFile {
@@ -62,6 +62,10 @@ pub async fn list(category: Option<S3FileCategory>) -> Result<Vec<S3FileDto>> {
     repository::list(category).await
 }
 
+pub async fn list_for_document(document_type: &str, document_id: &str) -> Result<Vec<S3FileDto>> {
+    repository::list_for_document(document_type, document_id).await
+}
+
 pub async fn upload(
     category: S3FileCategory,
     file: UploadedFile,
@@ -79,10 +83,31 @@ pub async fn upload(
         ));
     }
 
+    store(&cfg, category, file, uploaded_by_user_id).await
+}
+
+/// Сохраняет файл, сформированный сервером (например, фоновую выгрузку), — без лимита
+/// `max_upload_mb`, который относится к загрузкам пользователей.
+pub async fn store_generated(
+    category: S3FileCategory,
+    file: UploadedFile,
+    owner_user_id: Option<String>,
+) -> Result<S3FileDto> {
+    let cfg = s3_config()?;
+    store(&cfg, category, file, owner_user_id).await
+}
+
+async fn store(
+    cfg: &config::S3Config,
+    category: S3FileCategory,
+    file: UploadedFile,
+    uploaded_by_user_id: Option<String>,
+) -> Result<S3FileDto> {
+    let size = file.bytes.len() as u64;
     let id = Uuid::new_v4();
     let object_key = build_object_key(&category, id, &file.filename);
     let etag = client::put_object(
-        &cfg,
+        cfg,
         &object_key,
         file.content_type.as_deref(),
         file.bytes.clone(),
@@ -102,6 +127,8 @@ pub async fn upload(
         uploaded_by_user_id,
         created_at: now.clone(),
         updated_at: now,
+        document_type: None,
+        document_id: None,
     };
 
     repository::insert(&dto).await?;
@@ -128,7 +155,28 @@ pub async fn delete(id: &str) -> Result<bool> {
     let cfg = s3_config()?;
     client::delete_object(&cfg, &file.object_key).await?;
     let deleted_at = Utc::now().to_rfc3339();
-    repository::soft_delete(id, &deleted_at).await
+    let deleted = repository::soft_delete(id, &deleted_at).await?;
+    if deleted {
+        text_index::remove(id).await?;
+    }
+    Ok(deleted)
+}
+
+/// Привязывает файл к документу (или снимает привязку, если `document_type` пуст).
+pub async fn attach_to_document(
+    id: &str,
+    document_type: Option<&str>,
+    document_id: Option<&str>,
+) -> Result<bool> {
+    let document_type = document_type.map(str::trim).filter(|v| !v.is_empty());
+    let document_id = document_id.map(str::trim).filter(|v| !v.is_empty());
+    if document_type.is_some() != document_id.is_some() {
+        return Err(anyhow::anyhow!(
+            "Invalid document link: document_type and document_id go together"
+        ));
+    }
+    let updated_at = Utc::now().to_rfc3339();
+    repository::set_document(id, document_type, document_id, &updated_at).await
 }
 
 #[cfg(test)]
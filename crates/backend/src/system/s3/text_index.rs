@@ -0,0 +1,257 @@
+//! Индекс распознанного текста вложений: `sys_file_ocr` (состояние) и
+//! `sys_file_ocr_fts` (FTS5, поиск).
+
+use anyhow::Result;
+use chrono::Utc;
+use contracts::system::s3::{S3FileCategory, S3TextSearchHit};
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement, TransactionTrait, Value};
+
+use super::ocr::MAX_ATTEMPTS;
+use crate::shared::data::db::get_connection;
+
+/// Максимум слов поискового запроса.
+const MAX_QUERY_TERMS: usize = 10;
+
+/// Файл, ожидающий распознавания.
+#[derive(Debug, Clone)]
+pub struct PendingFile {
+    pub id: String,
+    pub original_filename: String,
+    pub content_type: Option<String>,
+    pub size_bytes: i64,
+}
+
+/// Условие «файл ждёт распознавания» для `sys_files_s3 f LEFT JOIN sys_file_ocr o`.
+fn pending_where(retry_failed: bool) -> String {
+    let retry = if retry_failed {
+        format!(
+            " OR (o.status = 'failed' AND o.attempts < {})",
+            MAX_ATTEMPTS
+        )
+    } else {
+        String::new()
+    };
+    format!(
+        "f.is_deleted = 0 AND f.category = '{}' AND (o.file_id IS NULL{})",
+        S3FileCategory::Documents.as_str(),
+        retry
+    )
+}
+
+pub async fn pending(limit: u64, retry_failed: bool) -> Result<Vec<PendingFile>> {
+    let sql = format!(
+        "SELECT f.id, f.original_filename, f.content_type, f.size_bytes
+         FROM sys_files_s3 f
+         LEFT JOIN sys_file_ocr o ON o.file_id = f.id
+         WHERE {}
+         ORDER BY f.created_at
+         LIMIT ?",
+        pending_where(retry_failed)
+    );
+    let rows = get_connection()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            sql,
+            [(limit.max(1) as i64).into()],
+        ))
+        .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(PendingFile {
+                id: row.try_get("", "id")?,
+                original_filename: row.try_get("", "original_filename")?,
+                content_type: row.try_get("", "content_type")?,
+                size_bytes: row.try_get("", "size_bytes")?,
+            })
+        })
+        .collect()
+}
+
+pub async fn pending_count() -> Result<i64> {
+    let sql = format!(
+        "SELECT COUNT(*) AS cnt
+         FROM sys_files_s3 f
+         LEFT JOIN sys_file_ocr o ON o.file_id = f.id
+         WHERE {}",
+        pending_where(false)
+    );
+    let row = get_connection()
+        .query_one(Statement::from_string(DatabaseBackend::Sqlite, sql))
+        .await?;
+    Ok(match row {
+        Some(row) => row.try_get("", "cnt")?,
+        None => 0,
+    })
+}
+
+async fn upsert_status(
+    conn: &impl ConnectionTrait,
+    file_id: &str,
+    status: &str,
+    engine: Option<&str>,
+    text_length: i64,
+    error: Option<&str>,
+) -> Result<()> {
+    conn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO sys_file_ocr (file_id, status, engine, text_length, error, attempts, processed_at)
+         VALUES (?, ?, ?, ?, ?, 1, ?)
+         ON CONFLICT(file_id) DO UPDATE SET
+            status = excluded.status,
+            engine = excluded.engine,
+            text_length = excluded.text_length,
+            error = excluded.error,
+            attempts = sys_file_ocr.attempts + 1,
+            processed_at = excluded.processed_at",
+        [
+            file_id.into(),
+            status.into(),
+            engine.map(str::to_string).into(),
+            text_length.into(),
+            error.map(str::to_string).into(),
+            Utc::now().to_rfc3339().into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Сохраняет распознанный текст и заменяет строку индекса.
+pub async fn save_text(file_id: &str, engine: &str, text: &str) -> Result<()> {
+    let txn = get_connection().begin().await?;
+    txn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM sys_file_ocr_fts WHERE file_id = ?",
+        [file_id.into()],
+    ))
+    .await?;
+    if !text.is_empty() {
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO sys_file_ocr_fts (file_id, content) VALUES (?, ?)",
+            [file_id.into(), text.into()],
+        ))
+        .await?;
+    }
+    upsert_status(
+        &txn,
+        file_id,
+        "done",
+        Some(engine),
+        text.chars().count() as i64,
+        None,
+    )
+    .await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+pub async fn save_skipped(file_id: &str, reason: &str) -> Result<()> {
+    upsert_status(get_connection(), file_id, "skipped", None, 0, Some(reason)).await
+}
+
+pub async fn save_failed(file_id: &str, engine: &str, error: &str) -> Result<()> {
+    upsert_status(
+        get_connection(),
+        file_id,
+        "failed",
+        Some(engine),
+        0,
+        Some(error),
+    )
+    .await
+}
+
+/// Убирает текст удалённого файла из индекса.
+pub async fn remove(file_id: &str) -> Result<()> {
+    get_connection()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM sys_file_ocr_fts WHERE file_id = ?",
+            [file_id.into()],
+        ))
+        .await?;
+    Ok(())
+}
+
+/// FTS5-запрос из пользовательского ввода: каждое слово — префиксный терм в кавычках
+/// (спецсимволы FTS5 не интерпретируются), все слова обязательны.
+pub fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .take(MAX_QUERY_TERMS)
+        .map(|term| format!("\"{}\"*", term.to_lowercase()))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Поиск по тексту вложений, лучшие совпадения первыми.
+pub async fn search(query: &str, limit: u64) -> Result<Vec<S3TextSearchHit>> {
+    let Some(match_expr) = fts_query(query) else {
+        return Ok(Vec::new());
+    };
+    let values: Vec<Value> = vec![match_expr.into(), (limit.max(1) as i64).into()];
+    let rows = get_connection()
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT f.id AS file_id, f.original_filename, f.category, f.document_type,
+                    f.document_id, f.created_at,
+                    snippet(sys_file_ocr_fts, 1, '[', ']', '…', 16) AS snippet
+             FROM sys_file_ocr_fts
+             JOIN sys_files_s3 f ON f.id = sys_file_ocr_fts.file_id
+             WHERE sys_file_ocr_fts MATCH ? AND f.is_deleted = 0
+             ORDER BY bm25(sys_file_ocr_fts)
+             LIMIT ?",
+            values,
+        ))
+        .await?;
+
+    rows.iter()
+        .map(|row| {
+            let category: String = row.try_get("", "category")?;
+            Ok(S3TextSearchHit {
+                file_id: row.try_get("", "file_id")?,
+                original_filename: row.try_get("", "original_filename")?,
+                category: S3FileCategory::from(category.as_str()),
+                document_type: row.try_get("", "document_type")?,
+                document_id: row.try_get("", "document_id")?,
+                snippet: row.try_get("", "snippet")?,
+                created_at: row.try_get("", "created_at")?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fts_query_quotes_terms_as_prefixes() {
+        assert_eq!(
+            fts_query("Акт сверки №12").as_deref(),
+            Some("\"акт\"* \"сверки\"* \"12\"*")
+        );
+    }
+
+    #[test]
+    fn fts_query_drops_fts_syntax() {
+        assert_eq!(
+            fts_query("invoice OR \"x\" NEAR(").as_deref(),
+            Some("\"invoice\"* \"or\"* \"x\"* \"near\"*")
+        );
+        assert_eq!(fts_query("  *:()  "), None);
+    }
+
+    #[test]
+    fn pending_filter_retries_failed_only_on_request() {
+        assert!(!pending_where(false).contains("failed"));
+        assert!(pending_where(true).contains("o.attempts < 3"));
+    }
+}
@@ -0,0 +1,8 @@
+//! Отложенное проведение документов (`/api/scheduled-posts`).
+//!
+//! Задание хранится в `sys_scheduled_posts`; исполнитель ([`service::run_loop`])
+//! раз в минуту проводит документы, время которых наступило. На документ — одно
+//! ожидающее задание: новое заменяет прежнее, отмена переводит его в `cancelled`.
+
+pub mod repository;
+pub mod service;
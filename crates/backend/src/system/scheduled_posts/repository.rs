@@ -0,0 +1,127 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{
+    ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QueryOrder, Set, Statement,
+};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sys_scheduled_posts")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub entity_type: String,
+    pub document_id: String,
+    pub post_at: String,
+    pub status: String,
+    pub error: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+    pub finished_at: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+fn conn() -> &'static DatabaseConnection {
+    get_connection()
+}
+
+pub async fn insert(model: Model) -> Result<(), DbErr> {
+    ActiveModel {
+        id: Set(model.id),
+        entity_type: Set(model.entity_type),
+        document_id: Set(model.document_id),
+        post_at: Set(model.post_at),
+        status: Set(model.status),
+        error: Set(model.error),
+        created_by: Set(model.created_by),
+        created_at: Set(model.created_at),
+        finished_at: Set(model.finished_at),
+    }
+    .insert(conn())
+    .await?;
+    Ok(())
+}
+
+pub async fn get_by_id(id: &str) -> Result<Option<Model>, DbErr> {
+    Entity::find_by_id(id.to_string()).one(conn()).await
+}
+
+/// Задание документа в статусе `status` (ожидающее — не более одного).
+pub async fn find_for_document(
+    entity_type: &str,
+    document_id: &str,
+    status: &str,
+) -> Result<Option<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::EntityType.eq(entity_type))
+        .filter(Column::DocumentId.eq(document_id))
+        .filter(Column::Status.eq(status))
+        .order_by_desc(Column::CreatedAt)
+        .one(conn())
+        .await
+}
+
+/// Задания в статусе `status` с `post_at <= now`, по порядку наступления.
+pub async fn list_due(status: &str, now: &str) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::Status.eq(status))
+        .filter(Column::PostAt.lte(now))
+        .order_by_asc(Column::PostAt)
+        .all(conn())
+        .await
+}
+
+/// Перевод задания из `from` в `to`; `false` — задание уже не в статусе `from`
+/// (например, отменено, пока исполнитель до него добирался).
+pub async fn transition(
+    id: &str,
+    from: &str,
+    to: &str,
+    error: Option<String>,
+    finished_at: &str,
+) -> Result<bool, DbErr> {
+    let result = conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_scheduled_posts SET status = ?1, error = ?2, finished_at = ?3 \
+             WHERE id = ?4 AND status = ?5",
+            vec![
+                to.into(),
+                error.into(),
+                finished_at.into(),
+                id.into(),
+                from.into(),
+            ],
+        ))
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Все задания документа из `from` в `to` (замена ожидающего задания новым).
+pub async fn transition_for_document(
+    entity_type: &str,
+    document_id: &str,
+    from: &str,
+    to: &str,
+    finished_at: &str,
+) -> Result<u64, DbErr> {
+    let result = conn()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_scheduled_posts SET status = ?1, finished_at = ?2 \
+             WHERE entity_type = ?3 AND document_id = ?4 AND status = ?5",
+            vec![
+                to.into(),
+                finished_at.into(),
+                entity_type.into(),
+                document_id.into(),
+                from.into(),
+            ],
+        ))
+        .await?;
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,190 @@
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use contracts::system::scheduled_posts::{
+    SchedulePostRequest, ScheduledPostDto, ScheduledPostStatus,
+};
+use uuid::Uuid;
+
+use super::repository;
+use crate::system::bulk_ops;
+use crate::system::operations::service::document_type_label;
+
+/// Как часто проверять наступившие задания: точность проведения — до минуты.
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+fn to_dto(model: repository::Model) -> ScheduledPostDto {
+    ScheduledPostDto {
+        status: ScheduledPostStatus::from_code(&model.status)
+            .unwrap_or(ScheduledPostStatus::Failed),
+        id: model.id,
+        entity_type: model.entity_type,
+        document_id: model.document_id,
+        post_at: model.post_at,
+        error: model.error,
+        created_by: model.created_by,
+        created_at: model.created_at,
+        finished_at: model.finished_at,
+    }
+}
+
+/// Проверка запроса; возвращает время проведения. Текст ошибки — для пользователя.
+pub fn validate(request: &SchedulePostRequest, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    if document_type_label(&request.entity_type).is_none() {
+        bail!(
+            "Отложенное проведение не поддерживается для '{}'",
+            request.entity_type
+        );
+    }
+    if Uuid::parse_str(&request.document_id).is_err() {
+        bail!("Некорректный id документа");
+    }
+    let Ok(post_at) = DateTime::parse_from_rfc3339(&request.post_at) else {
+        bail!("Некорректное время проведения");
+    };
+    let post_at = post_at.with_timezone(&Utc);
+    if post_at <= now {
+        bail!("Время проведения должно быть в будущем");
+    }
+    Ok(post_at)
+}
+
+/// Ставит документ в очередь; ожидающее задание по нему отменяется. Запрос должен
+/// пройти [`validate`].
+pub async fn schedule(
+    request: &SchedulePostRequest,
+    post_at: DateTime<Utc>,
+    username: &str,
+) -> Result<ScheduledPostDto> {
+    let now = Utc::now().to_rfc3339();
+    repository::transition_for_document(
+        &request.entity_type,
+        &request.document_id,
+        ScheduledPostStatus::Pending.code(),
+        ScheduledPostStatus::Cancelled.code(),
+        &now,
+    )
+    .await?;
+
+    let model = repository::Model {
+        id: Uuid::new_v4().to_string(),
+        entity_type: request.entity_type.clone(),
+        document_id: request.document_id.clone(),
+        post_at: post_at.to_rfc3339(),
+        status: ScheduledPostStatus::Pending.code().to_string(),
+        error: None,
+        created_by: username.to_string(),
+        created_at: now,
+        finished_at: None,
+    };
+    repository::insert(model.clone()).await?;
+    Ok(to_dto(model))
+}
+
+/// Ожидающее задание документа (для шапки формы).
+pub async fn get_pending(entity_type: &str, document_id: &str) -> Result<Option<ScheduledPostDto>> {
+    Ok(repository::find_for_document(
+        entity_type,
+        document_id,
+        ScheduledPostStatus::Pending.code(),
+    )
+    .await?
+    .map(to_dto))
+}
+
+pub async fn get(id: &str) -> Result<Option<ScheduledPostDto>> {
+    Ok(repository::get_by_id(id).await?.map(to_dto))
+}
+
+/// Отмена ожидающего задания; `false` — задание уже выполнено или отменено.
+pub async fn cancel(id: &str) -> Result<bool> {
+    Ok(repository::transition(
+        id,
+        ScheduledPostStatus::Pending.code(),
+        ScheduledPostStatus::Cancelled.code(),
+        None,
+        &Utc::now().to_rfc3339(),
+    )
+    .await?)
+}
+
+/// Проводит документы, время которых наступило. Возвращает число обработанных заданий.
+pub async fn run_due() -> Result<usize> {
+    let due = repository::list_due(
+        ScheduledPostStatus::Pending.code(),
+        &Utc::now().to_rfc3339(),
+    )
+    .await?;
+    for job in &due {
+        let outcome = match Uuid::parse_str(&job.document_id) {
+            Ok(id) => bulk_ops::service::post_document(&job.entity_type, id).await,
+            Err(e) => Err(e.into()),
+        };
+        let (status, error) = match outcome {
+            Ok(()) => (ScheduledPostStatus::Done, None),
+            Err(e) => {
+                tracing::warn!(
+                    "[scheduled_posts] {} {} failed: {e}",
+                    job.entity_type,
+                    job.document_id
+                );
+                (ScheduledPostStatus::Failed, Some(e.to_string()))
+            }
+        };
+        repository::transition(
+            &job.id,
+            ScheduledPostStatus::Pending.code(),
+            status.code(),
+            error,
+            &Utc::now().to_rfc3339(),
+        )
+        .await?;
+    }
+    Ok(due.len())
+}
+
+/// Исполнитель отложенного проведения. Не регламентное задание: планировщик может
+/// быть выключен в config.toml, а поставленные в очередь документы должны провестись.
+pub async fn run_loop() {
+    loop {
+        match run_due().await {
+            Ok(n) if n > 0 => {
+                tracing::info!("[scheduled_posts] processed {n} scheduled posting(s)")
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("[scheduled_posts] run failed: {e}"),
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn request(entity_type: &str, post_at: &str) -> SchedulePostRequest {
+        SchedulePostRequest {
+            entity_type: entity_type.to_string(),
+            document_id: "5f0c6a8e-2f5b-4b7e-9a43-0d7f7c1c2b11".to_string(),
+            post_at: post_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn validate_requires_supported_type_and_future_time() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        assert_eq!(
+            validate(&request("a013_ym_order", "2026-10-17T18:00:00+03:00"), now).unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 17, 15, 0, 0).unwrap()
+        );
+        assert!(validate(&request("a013_ym_order", "2026-10-17T14:00:00+03:00"), now).is_err());
+        assert!(validate(&request("a013_ym_order", "завтра"), now).is_err());
+        assert!(validate(&request("a015_wb_orders", "2026-10-18T00:00:00Z"), now).is_err());
+
+        let mut bad_id = request("a012_wb_sales", "2026-10-18T00:00:00Z");
+        bad_id.document_id = "42".to_string();
+        assert!(validate(&bad_id, now).is_err());
+    }
+}
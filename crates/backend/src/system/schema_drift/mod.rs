@@ -0,0 +1,80 @@
+//! Журнал расхождений схемы ответов маркетплейсов (`sys_schema_drift`).
+//!
+//! Записи приходят из `shared::marketplaces::schema_guard` при импорте. Новое
+//! расхождение открывается проблемой проверки качества `schema_drift` и рассылается
+//! администраторам; повторные появления только увеличивают счётчик.
+
+pub mod repository;
+
+use contracts::system::notifications::NotificationEvent;
+
+use crate::shared::marketplaces::schema_guard::SchemaDrift;
+use crate::system::notifications::dispatcher::{self, Notification};
+
+/// Сколько новых расхождений перечислять в уведомлении.
+const NOTIFY_LINES: usize = 20;
+
+/// Записывает расхождения страницы; о новых уведомляет администраторов в фоне.
+pub async fn record(endpoint: &str, mapping: &str, drifts: &[SchemaDrift]) -> anyhow::Result<()> {
+    let mut created = Vec::new();
+    for drift in drifts {
+        if repository::upsert(endpoint, mapping, drift).await? {
+            created.push(drift);
+        }
+    }
+    if created.is_empty() {
+        return Ok(());
+    }
+
+    tracing::warn!(
+        "schema drift on {}: {} new ({})",
+        endpoint,
+        created.len(),
+        created
+            .iter()
+            .map(|d| format!("{} {}", d.kind.code(), d.field))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let mut lines: Vec<String> = created
+        .iter()
+        .take(NOTIFY_LINES)
+        .map(|d| match &d.example {
+            Some(example) => format!("{}: {} = {}", d.kind.code(), d.field, example),
+            None => format!("{}: {}", d.kind.code(), d.field),
+        })
+        .collect();
+    if created.len() > NOTIFY_LINES {
+        lines.push(format!("… и ещё {}", created.len() - NOTIFY_LINES));
+    }
+    let notification = Notification {
+        event: NotificationEvent::AlertFired,
+        title: format!(
+            "Изменилась схема ответа {}: {} расхождений",
+            endpoint,
+            created.len()
+        ),
+        body: lines.join("\n"),
+        tab_key: Some(format!(
+            "quality_check_details_{}",
+            crate::quality::checks::schema_drift::CHECK_ID
+        )),
+        link: None,
+    };
+    tokio::spawn(async move {
+        match dispatcher::active_user_ids(true).await {
+            Ok(admins) => {
+                let report = dispatcher::dispatch(&notification, &admins).await;
+                if report.failed > 0 {
+                    tracing::warn!(
+                        "schema drift notification: {} deliveries failed",
+                        report.failed
+                    );
+                }
+            }
+            Err(e) => tracing::warn!("schema drift notification: {}", e),
+        }
+    });
+    Ok(())
+}
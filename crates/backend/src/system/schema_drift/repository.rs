@@ -0,0 +1,114 @@
+use chrono::Utc;
+use sea_orm::{ConnectionTrait, DbErr, FromQueryResult, Statement};
+
+use crate::shared::data::db::get_connection;
+use crate::shared::marketplaces::schema_guard::SchemaDrift;
+
+pub const STATUS_OPEN: &str = "open";
+pub const STATUS_ACKNOWLEDGED: &str = "acknowledged";
+
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct DriftRow {
+    pub id: String,
+    pub endpoint: String,
+    pub mapping: String,
+    pub kind: String,
+    pub field: String,
+    pub example_value: Option<String>,
+    pub occurrences: i64,
+    pub first_seen_at: String,
+    pub last_seen_at: String,
+    pub status: String,
+}
+
+/// Записывает расхождение: новое — открытой записью, известное — увеличивает
+/// счётчик и дату. Возвращает `true`, если запись создана.
+pub async fn upsert(endpoint: &str, mapping: &str, drift: &SchemaDrift) -> Result<bool, DbErr> {
+    let db = get_connection();
+    let now = Utc::now().to_rfc3339();
+    let inserted = db
+        .execute(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "INSERT OR IGNORE INTO sys_schema_drift \
+             (id, endpoint, mapping, kind, field, example_value, occurrences, \
+              first_seen_at, last_seen_at, status) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            vec![
+                uuid::Uuid::new_v4().to_string().into(),
+                endpoint.into(),
+                mapping.into(),
+                drift.kind.code().into(),
+                drift.field.clone().into(),
+                drift.example.clone().into(),
+                drift.count.into(),
+                now.clone().into(),
+                now.clone().into(),
+                STATUS_OPEN.into(),
+            ],
+        ))
+        .await?
+        .rows_affected()
+        > 0;
+    if !inserted {
+        db.execute(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "UPDATE sys_schema_drift \
+             SET occurrences = occurrences + ?, last_seen_at = ?, \
+                 example_value = COALESCE(?, example_value) \
+             WHERE endpoint = ? AND kind = ? AND field = ?",
+            vec![
+                drift.count.into(),
+                now.into(),
+                drift.example.clone().into(),
+                endpoint.into(),
+                drift.kind.code().into(),
+                drift.field.clone().into(),
+            ],
+        ))
+        .await?;
+    }
+    Ok(inserted)
+}
+
+/// Все расхождения: открытые сверху, затем по дате последнего появления.
+pub async fn list() -> Result<Vec<DriftRow>, DbErr> {
+    let db = get_connection();
+    DriftRow::find_by_statement(Statement::from_string(
+        db.get_database_backend(),
+        "SELECT id, endpoint, mapping, kind, field, example_value, occurrences, \
+                first_seen_at, last_seen_at, status \
+         FROM sys_schema_drift \
+         ORDER BY CASE status WHEN 'open' THEN 0 ELSE 1 END, last_seen_at DESC"
+            .to_string(),
+    ))
+    .all(db)
+    .await
+}
+
+/// Принимает расхождения: они остаются в журнале, но больше не считаются проблемой.
+pub async fn acknowledge(ids: &[String], username: &str) -> Result<u64, DbErr> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let db = get_connection();
+    let placeholders = vec!["?"; ids.len()].join(", ");
+    let mut values: Vec<sea_orm::Value> = vec![
+        STATUS_ACKNOWLEDGED.into(),
+        username.into(),
+        Utc::now().to_rfc3339().into(),
+        STATUS_OPEN.into(),
+    ];
+    values.extend(ids.iter().map(|id| id.clone().into()));
+    Ok(db
+        .execute(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &format!(
+                "UPDATE sys_schema_drift \
+                 SET status = ?, acknowledged_by = ?, acknowledged_at = ? \
+                 WHERE status = ? AND id IN ({placeholders})"
+            ),
+            values,
+        ))
+        .await?
+        .rows_affected())
+}
@@ -1,9 +1,21 @@
 use anyhow::Result;
+use contracts::projections::p904_sales_data::dto::ReturnNettingMode;
+use contracts::projections::p918_storage_cost_allocation::dto::StorageAllocationSettings;
+use contracts::system::auth::OidcSettings;
+use contracts::system::bulk_ops::{BULK_UNDO_WINDOW_DEFAULT_MINUTES, BULK_UNDO_WINDOW_MAX_MINUTES};
+use contracts::system::data_freshness::DataFreshnessSlaSettings;
 
 use super::repository;
 
 const KEY_SCHEDULER_ENABLED: &str = "scheduler_enabled";
 const KEY_RAW_JSON_CAPTURE_ENABLED: &str = "raw_json_capture_enabled";
+const KEY_P904_RETURN_NETTING_MODE: &str = "p904_return_netting_mode";
+const KEY_IMPORT_SUMMARY_SUBSCRIBERS: &str = "import_summary_subscribers";
+const KEY_BULK_UNDO_WINDOW_MINUTES: &str = "bulk_undo_window_minutes";
+const KEY_PROJECTION_ARCHIVE_BOUNDARY: &str = "projection_archive_boundary";
+const KEY_OIDC_CONFIG: &str = "oidc_config";
+const KEY_P918_STORAGE_ALLOCATION_RULES: &str = "p918_storage_allocation_rules";
+const KEY_DATA_FRESHNESS_SLA: &str = "data_freshness_sla";
 
 pub async fn get_scheduler_enabled() -> Result<bool> {
     let value = repository::get_setting(KEY_SCHEDULER_ENABLED).await?;
@@ -32,3 +44,106 @@ pub async fn set_raw_json_capture_enabled(enabled: bool) -> Result<()> {
     .await?;
     Ok(())
 }
+
+pub async fn get_p904_return_netting_mode() -> Result<ReturnNettingMode> {
+    let value = repository::get_setting(KEY_P904_RETURN_NETTING_MODE).await?;
+    Ok(value
+        .as_deref()
+        .and_then(ReturnNettingMode::from_code)
+        .unwrap_or_default())
+}
+
+pub async fn set_p904_return_netting_mode(mode: ReturnNettingMode) -> Result<()> {
+    repository::set_setting(KEY_P904_RETURN_NETTING_MODE, mode.code()).await?;
+    Ok(())
+}
+
+/// Id администраторов, подписанных на сводку плановых импортов.
+pub async fn get_import_summary_subscribers() -> Result<Vec<String>> {
+    let value = repository::get_setting(KEY_IMPORT_SUMMARY_SUBSCRIBERS).await?;
+    Ok(value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+pub async fn set_import_summary_subscribers(user_ids: &[String]) -> Result<()> {
+    repository::set_setting(KEY_IMPORT_SUMMARY_SUBSCRIBERS, &user_ids.join(",")).await?;
+    Ok(())
+}
+
+/// Окно отмены массовых операций, минут (0 — отмена выключена).
+pub async fn get_bulk_undo_window_minutes() -> Result<i64> {
+    let value = repository::get_setting(KEY_BULK_UNDO_WINDOW_MINUTES).await?;
+    Ok(value
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(BULK_UNDO_WINDOW_DEFAULT_MINUTES)
+        .clamp(0, BULK_UNDO_WINDOW_MAX_MINUTES))
+}
+
+pub async fn set_bulk_undo_window_minutes(minutes: i64) -> Result<()> {
+    let minutes = minutes.clamp(0, BULK_UNDO_WINDOW_MAX_MINUTES);
+    repository::set_setting(KEY_BULK_UNDO_WINDOW_MINUTES, &minutes.to_string()).await?;
+    Ok(())
+}
+
+/// Граница архивации проекций `YYYY-MM` (строки раньше неё — в *_archive).
+pub async fn get_projection_archive_boundary() -> Result<Option<String>> {
+    let value = repository::get_setting(KEY_PROJECTION_ARCHIVE_BOUNDARY).await?;
+    Ok(value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty()))
+}
+
+pub async fn set_projection_archive_boundary(month: &str) -> Result<()> {
+    repository::set_setting(KEY_PROJECTION_ARCHIVE_BOUNDARY, month).await?;
+    Ok(())
+}
+
+/// Настройки входа через OIDC (JSON); без записи — вход выключен.
+pub async fn get_oidc_settings() -> Result<OidcSettings> {
+    let value = repository::get_setting(KEY_OIDC_CONFIG).await?;
+    Ok(value
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default())
+}
+
+pub async fn set_oidc_settings(settings: &OidcSettings) -> Result<()> {
+    repository::set_setting(KEY_OIDC_CONFIG, &serde_json::to_string(settings)?).await?;
+    Ok(())
+}
+
+/// Правила распределения хранения и приёмки по SKU (JSON); без записи — драйверы по умолчанию.
+pub async fn get_p918_storage_allocation_settings() -> Result<StorageAllocationSettings> {
+    let value = repository::get_setting(KEY_P918_STORAGE_ALLOCATION_RULES).await?;
+    Ok(value
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default())
+}
+
+pub async fn set_p918_storage_allocation_settings(
+    settings: &StorageAllocationSettings,
+) -> Result<()> {
+    repository::set_setting(
+        KEY_P918_STORAGE_ALLOCATION_RULES,
+        &serde_json::to_string(settings)?,
+    )
+    .await?;
+    Ok(())
+}
+
+/// SLA свежести данных по источникам (JSON); без записи — значения по умолчанию.
+pub async fn get_data_freshness_sla() -> Result<DataFreshnessSlaSettings> {
+    let value = repository::get_setting(KEY_DATA_FRESHNESS_SLA).await?;
+    Ok(value
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default())
+}
+
+pub async fn set_data_freshness_sla(settings: &DataFreshnessSlaSettings) -> Result<()> {
+    repository::set_setting(KEY_DATA_FRESHNESS_SLA, &serde_json::to_string(settings)?).await?;
+    Ok(())
+}
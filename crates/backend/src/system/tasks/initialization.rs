@@ -17,8 +17,12 @@ use super::{
         Task015KbPostManager, Task016KbIntakeManager, Task017WbReturnsClaimsManager,
         Task018YmReturnsManager, Task019YmPaymentReportManager, Task020WbProductSnapshotManager,
         Task021MailIntakeManager, Task022MailReplyManager, Task023WbSalesFunnelDailyManager,
-        Task024WbSearchAnalyticsDailyManager, U501ImportUtManager, U502ImportOzonManager,
-        U503ImportYandexManager,
+        Task024WbSearchAnalyticsDailyManager, Task025ProjectionCompactionManager,
+        Task026StockAlertsManager, Task027AbcXyzClassificationManager,
+        Task028SalesAnomaliesManager, Task029DemandForecastManager, Task030AttachmentOcrManager,
+        Task031OzonReturnsManager, Task032ImportCoverageManager, Task033OzonFbsStatusSyncManager,
+        Task034UserAccessExpiryManager, Task035PostingConsistencyManager, U501ImportUtManager,
+        U502ImportOzonManager, U503ImportYandexManager,
     },
     registry::{set_global_registry, TaskManagerRegistry},
     worker::ScheduledTaskWorker,
@@ -64,6 +68,16 @@ pub async fn initialize_scheduled_tasks() -> Result<ScheduledTaskWorker> {
     let u503_executor = Arc::new(u503_import_from_yandex::ImportExecutor::new(u503_tracker));
     registry.register(U503ImportYandexManager::new(u503_executor));
 
+    // ---- Ozon atomic task managers ----
+
+    let task031_tracker = Arc::new(u502_import_from_ozon::ProgressTracker::new());
+    registry.register(Task031OzonReturnsManager::new(Arc::new(
+        u502_import_from_ozon::ImportExecutor::new(task031_tracker),
+    )));
+    registry.register(Task033OzonFbsStatusSyncManager::new());
+    registry.register(Task034UserAccessExpiryManager::new());
+    registry.register(Task035PostingConsistencyManager::new());
+
     // ---- WB atomic task managers — each owns its own executor + progress tracker ----
 
     registry.register(Task001WbOrdersFbsPollingManager::new(wb_executor!()));
@@ -101,6 +115,21 @@ pub async fn initialize_scheduled_tasks() -> Result<ScheduledTaskWorker> {
     registry.register(Task021MailIntakeManager::new());
     registry.register(Task022MailReplyManager::new());
 
+    // ---- Maintenance task managers ----
+    registry.register(Task025ProjectionCompactionManager::new());
+
+    // ---- Stock alert task managers ----
+    registry.register(Task026StockAlertsManager::new());
+
+    // ---- Analytics task managers ----
+    registry.register(Task027AbcXyzClassificationManager::new());
+    registry.register(Task028SalesAnomaliesManager::new());
+    registry.register(Task029DemandForecastManager::new());
+    registry.register(Task032ImportCoverageManager::new());
+
+    // ---- Attachment task managers ----
+    registry.register(Task030AttachmentOcrManager::new());
+
     let registry = Arc::new(registry);
     set_global_registry(Arc::clone(&registry));
 
@@ -126,6 +155,17 @@ mod tests {
             "task020_wb_product_snapshot",
             "task023_wb_sales_funnel_daily",
             "task024_wb_search_analytics_daily",
+            "task025_projection_compaction",
+            "task026_stock_alerts",
+            "task027_abc_xyz_classification",
+            "task028_sales_anomalies",
+            "task029_demand_forecast",
+            "task030_attachment_ocr",
+            "task031_ozon_returns",
+            "task032_import_coverage",
+            "task033_ozon_fbs_status_sync",
+            "task034_user_access_expiry",
+            "task035_posting_consistency",
         ] {
             let manager = registry
                 .get(task_type)
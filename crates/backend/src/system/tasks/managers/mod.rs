@@ -28,6 +28,17 @@ pub mod task021_mail_intake;
 pub mod task022_mail_reply;
 pub mod task023_wb_sales_funnel_daily;
 pub mod task024_wb_search_analytics_daily;
+pub mod task025_projection_compaction;
+pub mod task026_stock_alerts;
+pub mod task027_abc_xyz_classification;
+pub mod task028_sales_anomalies;
+pub mod task029_demand_forecast;
+pub mod task030_attachment_ocr;
+pub mod task031_ozon_returns;
+pub mod task032_import_coverage;
+pub mod task033_ozon_fbs_status_sync;
+pub mod task034_user_access_expiry;
+pub mod task035_posting_consistency;
 
 pub use u501_import_ut::U501ImportUtManager;
 pub use u502_import_ozon::U502ImportOzonManager;
@@ -57,3 +68,14 @@ pub use task021_mail_intake::Task021MailIntakeManager;
 pub use task022_mail_reply::Task022MailReplyManager;
 pub use task023_wb_sales_funnel_daily::Task023WbSalesFunnelDailyManager;
 pub use task024_wb_search_analytics_daily::Task024WbSearchAnalyticsDailyManager;
+pub use task025_projection_compaction::Task025ProjectionCompactionManager;
+pub use task026_stock_alerts::Task026StockAlertsManager;
+pub use task027_abc_xyz_classification::Task027AbcXyzClassificationManager;
+pub use task028_sales_anomalies::Task028SalesAnomaliesManager;
+pub use task029_demand_forecast::Task029DemandForecastManager;
+pub use task030_attachment_ocr::Task030AttachmentOcrManager;
+pub use task031_ozon_returns::Task031OzonReturnsManager;
+pub use task032_import_coverage::Task032ImportCoverageManager;
+pub use task033_ozon_fbs_status_sync::Task033OzonFbsStatusSyncManager;
+pub use task034_user_access_expiry::Task034UserAccessExpiryManager;
+pub use task035_posting_consistency::Task035PostingConsistencyManager;
@@ -2,7 +2,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use contracts::system::tasks::aggregate::ScheduledTask;
 use contracts::system::tasks::metadata::{
-    ExternalApiInfo, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+    ExternalApiInfo, TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
 };
 use contracts::system::tasks::progress::TaskProgress;
 use contracts::usecases::u504_import_from_wildberries::request::{ImportMode, ImportRequest};
@@ -82,6 +82,7 @@ static METADATA: TaskMetadata = TaskMetadata {
             max_value: Some(31),
         },
     ],
+    concurrency_class: TaskConcurrencyClass::MarketplaceImport,
     max_duration_seconds: 14400,
 };
 
@@ -191,6 +192,15 @@ impl TaskManager for Task002WbOrdersStatsHourlyManager {
         }
 
         logger.write_log(session_id, "task002 WB orders Statistics completed.")?;
+        if let Some(p) = self.executor.get_progress(session_id) {
+            logger.write_log(
+                session_id,
+                &format!(
+                    "task002 raw JSON: сохранено {}, дубликатов {}",
+                    p.raw_payloads_stored, p.raw_payloads_deduplicated
+                ),
+            )?;
+        }
         Ok(TaskRunOutcome::completed_loaded_to(date_to))
     }
 
@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use contracts::system::tasks::aggregate::ScheduledTask;
 use contracts::system::tasks::metadata::{
-    ExternalApiInfo, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+    ExternalApiInfo, TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
 };
 use contracts::system::tasks::progress::TaskProgress;
 use contracts::usecases::u504_import_from_wildberries::progress::ImportStatus;
@@ -111,6 +111,7 @@ static METADATA: TaskMetadata = TaskMetadata {
             max_value: Some(90),
         },
     ],
+    concurrency_class: TaskConcurrencyClass::MarketplaceImport,
     max_duration_seconds: 7200,
 };
 
@@ -180,6 +181,15 @@ impl TaskManager for Task004WbSalesManager {
             .await?;
 
         logger.write_log(session_id, "task004: WB Sales completed")?;
+        if let Some(p) = self.executor.get_progress(session_id) {
+            logger.write_log(
+                session_id,
+                &format!(
+                    "task004 raw JSON: сохранено {}, дубликатов {}",
+                    p.raw_payloads_stored, p.raw_payloads_deduplicated
+                ),
+            )?;
+        }
         let completed_with_errors = self
             .executor
             .get_progress(session_id)
@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use contracts::system::tasks::aggregate::ScheduledTask;
 use contracts::system::tasks::metadata::{
-    ExternalApiInfo, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+    ExternalApiInfo, TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
 };
 use contracts::system::tasks::progress::TaskProgress;
 use contracts::usecases::u504_import_from_wildberries::request::{ImportMode, ImportRequest};
@@ -65,6 +65,8 @@ static METADATA: TaskMetadata = TaskMetadata {
         "Финансовые данные доступны с задержкой 3–5 дней после продажи",
         "overlap_days (по умолчанию 3) компенсирует задержку появления данных",
         "chunk_days (по умолчанию 7) — максимальный диапазон за один запуск",
+        "При таймауте или 5xx страница запрашивается повторно (до 3 раз) с того же rrdid",
+        "После сбоя повторный запуск продолжает с последнего загруженного дня (контрольная точка, 48 ч)",
         "Рекомендуется запускать 1 раз в день в ночное время",
     ],
     config_fields: &[
@@ -109,6 +111,7 @@ static METADATA: TaskMetadata = TaskMetadata {
             max_value: Some(90),
         },
     ],
+    concurrency_class: TaskConcurrencyClass::FinanceImport,
     max_duration_seconds: 14400,
 };
 
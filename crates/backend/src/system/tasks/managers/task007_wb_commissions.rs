@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use chrono::Utc;
 use contracts::system::tasks::aggregate::ScheduledTask;
 use contracts::system::tasks::metadata::{
-    ExternalApiInfo, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+    ExternalApiInfo, TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
 };
 use contracts::system::tasks::progress::TaskProgress;
 use contracts::usecases::u504_import_from_wildberries::request::{ImportMode, ImportRequest};
@@ -54,6 +54,7 @@ static METADATA: TaskMetadata = TaskMetadata {
         min_value: None,
         max_value: None,
     }],
+    concurrency_class: TaskConcurrencyClass::MarketplaceImport,
     max_duration_seconds: 3600,
 };
 
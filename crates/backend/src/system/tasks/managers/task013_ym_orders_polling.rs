@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use chrono::{Duration, Utc};
 use contracts::system::tasks::aggregate::ScheduledTask;
 use contracts::system::tasks::metadata::{
-    ExternalApiInfo, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+    ExternalApiInfo, TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
 };
 use contracts::system::tasks::progress::TaskProgress;
 use contracts::usecases::u503_import_from_yandex::progress::ImportStatus;
@@ -84,6 +84,7 @@ static METADATA: TaskMetadata = TaskMetadata {
             max_value: Some(1440),
         },
     ],
+    concurrency_class: TaskConcurrencyClass::Polling,
     max_duration_seconds: 1800,
 };
 
@@ -2,7 +2,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use contracts::domain::common::AggregateId;
 use contracts::system::tasks::aggregate::ScheduledTask;
-use contracts::system::tasks::metadata::TaskMetadata;
+use contracts::system::tasks::metadata::{TaskConcurrencyClass, TaskMetadata};
 use contracts::system::tasks::progress::TaskProgress;
 use std::sync::Arc;
 
@@ -24,6 +24,7 @@ static METADATA: TaskMetadata = TaskMetadata {
         "После записи документов перезагружает in-memory индекс KB",
     ],
     config_fields: &[],
+    concurrency_class: TaskConcurrencyClass::Communication,
     max_duration_seconds: 3600,
 };
 
@@ -2,7 +2,9 @@ use anyhow::Result;
 use async_trait::async_trait;
 use contracts::domain::common::AggregateId;
 use contracts::system::tasks::aggregate::ScheduledTask;
-use contracts::system::tasks::metadata::{TaskConfigField, TaskConfigFieldType, TaskMetadata};
+use contracts::system::tasks::metadata::{
+    TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+};
 use contracts::system::tasks::progress::TaskProgress;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -46,6 +48,7 @@ static METADATA: TaskMetadata = TaskMetadata {
             max_value: Some(7),
         },
     ],
+    concurrency_class: TaskConcurrencyClass::Communication,
     max_duration_seconds: 1800,
 };
 
@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use contracts::system::tasks::aggregate::ScheduledTask;
 use contracts::system::tasks::metadata::{
-    ExternalApiInfo, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+    ExternalApiInfo, TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
 };
 use contracts::system::tasks::progress::TaskProgress;
 use contracts::usecases::u503_import_from_yandex::progress::ImportStatus;
@@ -133,6 +133,7 @@ static METADATA: TaskMetadata = TaskMetadata {
             max_value: Some(365),
         },
     ],
+    concurrency_class: TaskConcurrencyClass::FinanceImport,
     max_duration_seconds: 7200,
 };
 
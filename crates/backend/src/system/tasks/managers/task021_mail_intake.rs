@@ -13,7 +13,9 @@ use contracts::domain::a038_llm_connection::aggregate::AgentType;
 use contracts::domain::a039_mail_message::aggregate::status;
 use contracts::domain::common::AggregateId;
 use contracts::system::tasks::aggregate::ScheduledTask;
-use contracts::system::tasks::metadata::{TaskConfigField, TaskConfigFieldType, TaskMetadata};
+use contracts::system::tasks::metadata::{
+    TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+};
 use contracts::system::tasks::progress::TaskProgress;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -64,6 +66,7 @@ static METADATA: TaskMetadata = TaskMetadata {
             max_value: Some(1440),
         },
     ],
+    concurrency_class: TaskConcurrencyClass::Communication,
     max_duration_seconds: 1800,
 };
 
@@ -11,7 +11,9 @@ use async_trait::async_trait;
 use chrono::Utc;
 use contracts::domain::a039_mail_message::aggregate::{status, MailMessage};
 use contracts::system::tasks::aggregate::ScheduledTask;
-use contracts::system::tasks::metadata::{TaskConfigField, TaskConfigFieldType, TaskMetadata};
+use contracts::system::tasks::metadata::{
+    TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+};
 use contracts::system::tasks::progress::TaskProgress;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -43,6 +45,7 @@ static METADATA: TaskMetadata = TaskMetadata {
         min_value: Some(1),
         max_value: Some(50),
     }],
+    concurrency_class: TaskConcurrencyClass::Communication,
     max_duration_seconds: 600,
 };
 
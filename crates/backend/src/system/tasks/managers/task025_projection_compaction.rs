@@ -0,0 +1,183 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use contracts::system::tasks::aggregate::ScheduledTask;
+use contracts::system::tasks::metadata::{
+    TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+};
+use contracts::system::tasks::progress::TaskProgress;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::shared::data::{projection_compaction, raw_storage};
+use crate::system::tasks::logger::TaskLogger;
+use crate::system::tasks::manager::{TaskManager, TaskRunOutcome};
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    #[serde(default)]
+    dry_run: i64,
+    #[serde(default)]
+    reindex: i64,
+    #[serde(default = "default_vacuum")]
+    vacuum: i64,
+}
+
+fn default_vacuum() -> i64 {
+    1
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            dry_run: 0,
+            reindex: 0,
+            vacuum: default_vacuum(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Metadata
+// ---------------------------------------------------------------------------
+
+static METADATA: TaskMetadata = TaskMetadata {
+    task_type: "task025_projection_compaction",
+    write_tables: projection_compaction::COMPACTION_TABLES,
+    display_name: "Обслуживание — компактизация проекций",
+    description: "Удаляет из проекций p900/p904 строки, регистратор которых распроведён, помечен \
+        на удаление или отсутствует (копятся после частых распроведений/перепроведений), затем \
+        по настройке выполняет REINDEX и VACUUM. В логе — отчёт по таблицам и освобождённому месту.",
+    external_apis: &[],
+    constraints: &[
+        "VACUUM держит запись в БД занятой на всё время выполнения — запускать вне рабочих часов",
+        "Типы регистраторов вне карты источников не удаляются",
+        "dry_run = 1 только считает мёртвые строки, ничего не удаляя",
+    ],
+    config_fields: &[
+        TaskConfigField {
+            key: "dry_run",
+            label: "Только отчёт (dry-run)",
+            hint: "1 — посчитать мёртвые строки без удаления, 0 — удалить",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("0"),
+            min_value: Some(0),
+            max_value: Some(1),
+        },
+        TaskConfigField {
+            key: "reindex",
+            label: "REINDEX проекций",
+            hint: "1 — пересобрать индексы компактизированных таблиц",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("0"),
+            min_value: Some(0),
+            max_value: Some(1),
+        },
+        TaskConfigField {
+            key: "vacuum",
+            label: "VACUUM после очистки",
+            hint: "1 — вернуть освободившиеся страницы файлу БД",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("1"),
+            min_value: Some(0),
+            max_value: Some(1),
+        },
+    ],
+    concurrency_class: TaskConcurrencyClass::ProjectionRebuild,
+    max_duration_seconds: 3600,
+};
+
+pub struct Task025ProjectionCompactionManager;
+
+impl Task025ProjectionCompactionManager {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl TaskManager for Task025ProjectionCompactionManager {
+    fn task_type(&self) -> &'static str {
+        "task025_projection_compaction"
+    }
+
+    fn metadata(&self) -> &'static TaskMetadata {
+        &METADATA
+    }
+
+    async fn run(
+        &self,
+        task: &ScheduledTask,
+        session_id: &str,
+        logger: Arc<TaskLogger>,
+    ) -> Result<TaskRunOutcome> {
+        let config: Config = serde_json::from_str(&task.config_json).unwrap_or_default();
+        let dry_run = config.dry_run != 0;
+
+        logger.write_log(
+            session_id,
+            &format!(
+                "Projection compaction started: dry_run={}, reindex={}, vacuum={}",
+                dry_run,
+                config.reindex != 0,
+                config.vacuum != 0
+            ),
+        )?;
+
+        let report = projection_compaction::compact_projections(dry_run).await?;
+        for table in &report.tables {
+            logger.write_log(
+                session_id,
+                &format!(
+                    "{}: rows={}, stale={}, deleted={}",
+                    table.table, table.rows_before, table.stale_rows, table.deleted_rows
+                ),
+            )?;
+        }
+        logger.write_log(
+            session_id,
+            &format!(
+                "Compaction total: stale={}, deleted={}",
+                report.total_stale(),
+                report.total_deleted()
+            ),
+        )?;
+
+        if dry_run {
+            return Ok(TaskRunOutcome::completed());
+        }
+
+        if config.reindex != 0 {
+            projection_compaction::reindex_projections().await?;
+            logger.write_log(session_id, "REINDEX completed")?;
+        }
+
+        if config.vacuum != 0 {
+            let vacuum = raw_storage::vacuum().await?;
+            logger.write_log(
+                session_id,
+                &format!(
+                    "VACUUM: {:.1} MB -> {:.1} MB, freed {:.1} MB in {} ms (wal {:.1} -> {:.1} MB)",
+                    vacuum.file_mb_before,
+                    vacuum.file_mb_after,
+                    vacuum.freed_mb,
+                    vacuum.duration_ms,
+                    vacuum.wal_mb_before,
+                    vacuum.wal_mb_after
+                ),
+            )?;
+        }
+
+        Ok(TaskRunOutcome::completed())
+    }
+
+    fn get_progress(&self, _session_id: &str) -> Option<TaskProgress> {
+        None
+    }
+}
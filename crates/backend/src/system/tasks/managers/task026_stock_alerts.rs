@@ -0,0 +1,238 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use contracts::system::notifications::NotificationEvent;
+use contracts::system::tasks::aggregate::ScheduledTask;
+use contracts::system::tasks::metadata::{
+    TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+};
+use contracts::system::tasks::progress::TaskProgress;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::domain::a007_marketplace_product::stock_alerts::{self, StockAlertThresholds};
+use crate::shared::mail;
+use crate::system::notifications::dispatcher::{self, Notification};
+use crate::system::tasks::logger::TaskLogger;
+use crate::system::tasks::manager::{TaskManager, TaskRunOutcome};
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    #[serde(default = "default_low_cover_days")]
+    low_cover_days: i64,
+    #[serde(default = "default_stale_days")]
+    stale_days: i64,
+    #[serde(default = "default_velocity_days")]
+    velocity_days: i64,
+    /// Адреса через запятую; пусто — только бейджи в списке товаров.
+    #[serde(default)]
+    recipients: String,
+}
+
+fn default_low_cover_days() -> i64 {
+    14
+}
+
+fn default_stale_days() -> i64 {
+    60
+}
+
+fn default_velocity_days() -> i64 {
+    28
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            low_cover_days: default_low_cover_days(),
+            stale_days: default_stale_days(),
+            velocity_days: default_velocity_days(),
+            recipients: String::new(),
+        }
+    }
+}
+
+impl Config {
+    fn recipients(&self) -> Vec<String> {
+        self.recipients
+            .split([',', ';'])
+            .map(str::trim)
+            .filter(|addr| !addr.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Metadata
+// ---------------------------------------------------------------------------
+
+static METADATA: TaskMetadata = TaskMetadata {
+    task_type: "task026_stock_alerts",
+    write_tables: &["a007_stock_alerts"],
+    display_name: "Склад — алерты по остаткам WB",
+    description: "По последнему снимку остатков a037 и продажам a012 находит товары, которых \
+        хватит меньше чем на заданное число дней, и товары без продаж дольше заданного срока \
+        при ненулевом остатке. Результат показывается бейджами в списке товаров; о новых \
+        алертах уведомляются пользователи (по их настройкам уведомлений) и получатели из конфига.",
+    external_apis: &[],
+    constraints: &[
+        "Только WB: остатки берутся из снимков a037 (task020) — запускать после него",
+        "Письмо уходит только о новых алертах, которых не было в прошлом расчёте",
+        "Отправка писем требует включённой секции [mail] в config.toml",
+    ],
+    config_fields: &[
+        TaskConfigField {
+            key: "low_cover_days",
+            label: "Порог запаса, дней",
+            hint: "Алерт, если остатка хватит меньше чем на столько дней",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("14"),
+            min_value: Some(1),
+            max_value: Some(365),
+        },
+        TaskConfigField {
+            key: "stale_days",
+            label: "Без продаж, дней",
+            hint: "Алерт, если остаток есть, а продаж не было столько дней",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("60"),
+            min_value: Some(1),
+            max_value: Some(730),
+        },
+        TaskConfigField {
+            key: "velocity_days",
+            label: "Окно скорости продаж, дней",
+            hint: "За сколько последних дней считать среднюю скорость продаж",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("28"),
+            min_value: Some(7),
+            max_value: Some(180),
+        },
+        TaskConfigField {
+            key: "recipients",
+            label: "Дополнительные получатели",
+            hint:
+                "Email через запятую, помимо пользователей с включённым событием «Сработал алерт»",
+            field_type: TaskConfigFieldType::Text,
+            required: false,
+            default_value: None,
+            min_value: None,
+            max_value: None,
+        },
+    ],
+    concurrency_class: TaskConcurrencyClass::Analytics,
+    max_duration_seconds: 600,
+};
+
+pub struct Task026StockAlertsManager;
+
+impl Task026StockAlertsManager {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl TaskManager for Task026StockAlertsManager {
+    fn task_type(&self) -> &'static str {
+        "task026_stock_alerts"
+    }
+
+    fn metadata(&self) -> &'static TaskMetadata {
+        &METADATA
+    }
+
+    async fn run(
+        &self,
+        task: &ScheduledTask,
+        session_id: &str,
+        logger: Arc<TaskLogger>,
+    ) -> Result<TaskRunOutcome> {
+        let config: Config = serde_json::from_str(&task.config_json).unwrap_or_default();
+        let thresholds = StockAlertThresholds {
+            low_cover_days: config.low_cover_days,
+            stale_days: config.stale_days,
+            velocity_window_days: config.velocity_days,
+        };
+
+        logger.write_log(
+            session_id,
+            &format!(
+                "Stock alerts started: low_cover_days={}, stale_days={}, velocity_days={}",
+                thresholds.low_cover_days, thresholds.stale_days, thresholds.velocity_window_days
+            ),
+        )?;
+
+        let now = chrono::Utc::now();
+        let alerts = stock_alerts::compute(&thresholds, now.date_naive()).await?;
+        let new_alerts = stock_alerts::replace_all(&alerts, &now.to_rfc3339()).await?;
+
+        logger.write_log(
+            session_id,
+            &format!("Алертов: {} (новых: {})", alerts.len(), new_alerts.len()),
+        )?;
+
+        if new_alerts.is_empty() {
+            return Ok(TaskRunOutcome::completed());
+        }
+
+        let subject = format!("Складские алерты WB: {} новых", new_alerts.len());
+        let body = stock_alerts::notification_text(&new_alerts, &thresholds);
+        let mut failed = false;
+
+        // Пользователям — по их настройкам уведомлений (событие «Сработал алерт»)
+        let notification = Notification {
+            event: NotificationEvent::AlertFired,
+            title: subject.clone(),
+            body: body.clone(),
+            tab_key: Some("a007_marketplace_product".to_string()),
+            link: None,
+        };
+        let report =
+            dispatcher::dispatch(&notification, &dispatcher::active_user_ids(false).await?).await;
+        logger.write_log(
+            session_id,
+            &format!(
+                "Уведомления пользователям: в приложении {}, email {}, Telegram {}, ошибок {}",
+                report.in_app, report.email, report.telegram, report.failed
+            ),
+        )?;
+        failed |= report.failed > 0;
+
+        // Внешние адреса из конфига задачи
+        let recipients = config.recipients();
+        for recipient in &recipients {
+            if let Err(e) = mail::check_and_record_send() {
+                logger.write_log(session_id, &format!("Rate-limit отправки: {e}"))?;
+                failed = true;
+                break;
+            }
+            match mail::send_email(recipient, &subject, &body).await {
+                Ok(()) => {
+                    logger.write_log(session_id, &format!("Письмо отправлено: {recipient}"))?
+                }
+                Err(e) => {
+                    failed = true;
+                    logger.write_log(session_id, &format!("{recipient}: ошибка отправки: {e}"))?;
+                }
+            }
+        }
+
+        if failed {
+            Ok(TaskRunOutcome::completed_with_errors())
+        } else {
+            Ok(TaskRunOutcome::completed())
+        }
+    }
+
+    fn get_progress(&self, _session_id: &str) -> Option<TaskProgress> {
+        None
+    }
+}
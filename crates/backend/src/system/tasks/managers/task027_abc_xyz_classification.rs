@@ -0,0 +1,225 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use contracts::system::tasks::aggregate::ScheduledTask;
+use contracts::system::tasks::metadata::{
+    TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+};
+use contracts::system::tasks::progress::TaskProgress;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::domain::a007_marketplace_product::abc_xyz::{self, AbcXyzThresholds};
+use crate::system::tasks::logger::TaskLogger;
+use crate::system::tasks::manager::{TaskManager, TaskRunOutcome};
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    #[serde(default = "default_quarters")]
+    quarters: i64,
+    #[serde(default = "default_a_share")]
+    a_share: i64,
+    #[serde(default = "default_b_share")]
+    b_share: i64,
+    #[serde(default = "default_x_max_cv")]
+    x_max_cv: i64,
+    #[serde(default = "default_y_max_cv")]
+    y_max_cv: i64,
+}
+
+fn default_quarters() -> i64 {
+    2
+}
+
+fn default_a_share() -> i64 {
+    80
+}
+
+fn default_b_share() -> i64 {
+    95
+}
+
+fn default_x_max_cv() -> i64 {
+    25
+}
+
+fn default_y_max_cv() -> i64 {
+    50
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            quarters: default_quarters(),
+            a_share: default_a_share(),
+            b_share: default_b_share(),
+            x_max_cv: default_x_max_cv(),
+            y_max_cv: default_y_max_cv(),
+        }
+    }
+}
+
+impl Config {
+    fn thresholds(&self) -> AbcXyzThresholds {
+        AbcXyzThresholds {
+            a_share: self.a_share as f64,
+            b_share: self.b_share.max(self.a_share) as f64,
+            x_max_cv: self.x_max_cv as f64,
+            y_max_cv: self.y_max_cv.max(self.x_max_cv) as f64,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Metadata
+// ---------------------------------------------------------------------------
+
+static METADATA: TaskMetadata = TaskMetadata {
+    task_type: "task027_abc_xyz_classification",
+    write_tables: &["a007_abc_xyz_classes"],
+    display_name: "Аналитика — ABC/XYZ-классификация товаров",
+    description: "По регистру продаж p900 для каждого завершённого квартала относит товары \
+        маркетплейсов к классам ABC (доля в выручке) и XYZ (вариация недельного спроса). \
+        Классы последнего квартала показываются метками и фильтрами в списке товаров, \
+        сценарии маржи и воронке продаж.",
+    external_apis: &[],
+    constraints: &[
+        "Пересчитываются последние завершённые кварталы; текущий квартал не классифицируется",
+        "Классифицируются только товары с продажами в квартале и ссылкой на a007 в p900",
+    ],
+    config_fields: &[
+        TaskConfigField {
+            key: "quarters",
+            label: "Кварталов для пересчёта",
+            hint: "Сколько последних завершённых кварталов пересчитывать за запуск",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("2"),
+            min_value: Some(1),
+            max_value: Some(12),
+        },
+        TaskConfigField {
+            key: "a_share",
+            label: "Граница A, % выручки",
+            hint: "Товары, набирающие первые N % выручки квартала, — класс A",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("80"),
+            min_value: Some(1),
+            max_value: Some(99),
+        },
+        TaskConfigField {
+            key: "b_share",
+            label: "Граница B, % выручки",
+            hint: "Товары до этой накопленной доли выручки — класс B, остальные — C",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("95"),
+            min_value: Some(1),
+            max_value: Some(100),
+        },
+        TaskConfigField {
+            key: "x_max_cv",
+            label: "Граница X, вариация %",
+            hint: "Коэффициент вариации недельных продаж не выше — класс X",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("25"),
+            min_value: Some(1),
+            max_value: Some(500),
+        },
+        TaskConfigField {
+            key: "y_max_cv",
+            label: "Граница Y, вариация %",
+            hint: "Коэффициент вариации не выше — класс Y, иначе Z",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("50"),
+            min_value: Some(1),
+            max_value: Some(1000),
+        },
+    ],
+    concurrency_class: TaskConcurrencyClass::Analytics,
+    max_duration_seconds: 900,
+};
+
+pub struct Task027AbcXyzClassificationManager;
+
+impl Task027AbcXyzClassificationManager {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl TaskManager for Task027AbcXyzClassificationManager {
+    fn task_type(&self) -> &'static str {
+        "task027_abc_xyz_classification"
+    }
+
+    fn metadata(&self) -> &'static TaskMetadata {
+        &METADATA
+    }
+
+    async fn run(
+        &self,
+        task: &ScheduledTask,
+        session_id: &str,
+        logger: Arc<TaskLogger>,
+    ) -> Result<TaskRunOutcome> {
+        let config: Config = serde_json::from_str(&task.config_json).unwrap_or_default();
+        let thresholds = config.thresholds();
+        let now = chrono::Utc::now();
+        let quarters =
+            abc_xyz::completed_quarters(now.date_naive(), config.quarters.clamp(1, 12) as usize);
+
+        logger.write_log(
+            session_id,
+            &format!(
+                "ABC/XYZ started: quarters={}, A<{}%, B<{}%, X<={}%, Y<={}%",
+                quarters.len(),
+                thresholds.a_share,
+                thresholds.b_share,
+                thresholds.x_max_cv,
+                thresholds.y_max_cv
+            ),
+        )?;
+
+        let computed_at = now.to_rfc3339();
+        for quarter in quarters {
+            let rows = abc_xyz::compute_quarter(quarter, &thresholds).await?;
+            abc_xyz::replace_quarter(quarter, &rows, &computed_at).await?;
+
+            let count = |abc: &str, xyz: &str| {
+                rows.iter()
+                    .filter(|row| abc.is_empty() || row.abc.code() == abc)
+                    .filter(|row| xyz.is_empty() || row.xyz.code() == xyz)
+                    .count()
+            };
+            logger.write_log(
+                session_id,
+                &format!(
+                    "{}: товаров {} — A {}, B {}, C {}; X {}, Y {}, Z {}; CZ {}",
+                    quarter.code(),
+                    rows.len(),
+                    count("A", ""),
+                    count("B", ""),
+                    count("C", ""),
+                    count("", "X"),
+                    count("", "Y"),
+                    count("", "Z"),
+                    count("C", "Z")
+                ),
+            )?;
+        }
+
+        Ok(TaskRunOutcome::completed())
+    }
+
+    fn get_progress(&self, _session_id: &str) -> Option<TaskProgress> {
+        None
+    }
+}
@@ -0,0 +1,249 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use contracts::system::notifications::NotificationEvent;
+use contracts::system::tasks::aggregate::ScheduledTask;
+use contracts::system::tasks::metadata::{
+    TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+};
+use contracts::system::tasks::progress::TaskProgress;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::domain::a007_marketplace_product::sales_anomalies::{self, AnomalyThresholds};
+use crate::system::notifications::dispatcher::{self, Notification};
+use crate::system::tasks::logger::TaskLogger;
+use crate::system::tasks::manager::{TaskManager, TaskRunOutcome};
+
+/// Сколько аномалий перечислять в тексте уведомления.
+const NOTIFICATION_LINES: usize = 20;
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    #[serde(default = "default_check_days")]
+    check_days: i64,
+    #[serde(default = "default_lag_days")]
+    lag_days: i64,
+    #[serde(default = "default_history_weeks")]
+    history_weeks: i64,
+    #[serde(default = "default_z_threshold")]
+    z_threshold: i64,
+    #[serde(default = "default_min_qty_delta")]
+    min_qty_delta: i64,
+}
+
+fn default_check_days() -> i64 {
+    3
+}
+
+fn default_lag_days() -> i64 {
+    1
+}
+
+fn default_history_weeks() -> i64 {
+    8
+}
+
+fn default_z_threshold() -> i64 {
+    3
+}
+
+fn default_min_qty_delta() -> i64 {
+    5
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            check_days: default_check_days(),
+            lag_days: default_lag_days(),
+            history_weeks: default_history_weeks(),
+            z_threshold: default_z_threshold(),
+            min_qty_delta: default_min_qty_delta(),
+        }
+    }
+}
+
+impl Config {
+    fn thresholds(&self) -> AnomalyThresholds {
+        let history_weeks = self.history_weeks.clamp(2, 52);
+        AnomalyThresholds {
+            history_weeks,
+            min_history: (history_weeks as usize / 2).max(2),
+            z_threshold: self.z_threshold.max(1) as f64,
+            min_qty_delta: self.min_qty_delta.max(0) as f64,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Metadata
+// ---------------------------------------------------------------------------
+
+static METADATA: TaskMetadata = TaskMetadata {
+    task_type: "task028_sales_anomalies",
+    write_tables: &["a007_sales_anomalies"],
+    display_name: "Аналитика — аномалии дневных продаж",
+    description: "По регистру продаж p900 сравнивает продажи последних дней по каждому товару \
+        и итогом по кабинету с тем же днём недели за предыдущие недели (сезонный z-score). \
+        Сильные всплески и провалы попадают в список на разбор «Аномалии продаж», о новых \
+        уведомляются пользователи (событие «Сработал алерт»).",
+    external_apis: &[],
+    constraints: &[
+        "Запускать после обновления регистра продаж p900",
+        "Пересчитываются последние дни: неразобранная аномалия, исчезнувшая после догрузки данных, удаляется",
+        "Разобранные аномалии (с пояснением) не перезаписываются",
+    ],
+    config_fields: &[
+        TaskConfigField {
+            key: "check_days",
+            label: "Проверять дней",
+            hint: "Сколько последних дней проверять за запуск",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("3"),
+            min_value: Some(1),
+            max_value: Some(60),
+        },
+        TaskConfigField {
+            key: "lag_days",
+            label: "Отставание данных, дней",
+            hint: "Последние дни без полных данных маркетплейса не проверяются",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("1"),
+            min_value: Some(0),
+            max_value: Some(14),
+        },
+        TaskConfigField {
+            key: "history_weeks",
+            label: "История, недель",
+            hint: "За сколько предыдущих недель брать тот же день недели для ожидания",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("8"),
+            min_value: Some(2),
+            max_value: Some(52),
+        },
+        TaskConfigField {
+            key: "z_threshold",
+            label: "Порог, сигм",
+            hint: "Аномалия, если продажи отличаются от ожидания больше чем на столько сигм",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("3"),
+            min_value: Some(1),
+            max_value: Some(10),
+        },
+        TaskConfigField {
+            key: "min_qty_delta",
+            label: "Минимальное отклонение, шт.",
+            hint: "Меньшие отклонения не считаются аномалией даже при большом z",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("5"),
+            min_value: Some(0),
+            max_value: Some(10000),
+        },
+    ],
+    concurrency_class: TaskConcurrencyClass::Analytics,
+    max_duration_seconds: 900,
+};
+
+pub struct Task028SalesAnomaliesManager;
+
+impl Task028SalesAnomaliesManager {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl TaskManager for Task028SalesAnomaliesManager {
+    fn task_type(&self) -> &'static str {
+        "task028_sales_anomalies"
+    }
+
+    fn metadata(&self) -> &'static TaskMetadata {
+        &METADATA
+    }
+
+    async fn run(
+        &self,
+        task: &ScheduledTask,
+        session_id: &str,
+        logger: Arc<TaskLogger>,
+    ) -> Result<TaskRunOutcome> {
+        let config: Config = serde_json::from_str(&task.config_json).unwrap_or_default();
+        let thresholds = config.thresholds();
+        let now = chrono::Utc::now();
+        let check_to = now.date_naive() - chrono::Duration::days(config.lag_days.clamp(0, 14));
+        let check_from = check_to - chrono::Duration::days(config.check_days.clamp(1, 60) - 1);
+        let check_from_str = check_from.format("%Y-%m-%d").to_string();
+        let check_to_str = check_to.format("%Y-%m-%d").to_string();
+
+        logger.write_log(
+            session_id,
+            &format!(
+                "Sales anomalies started: days {}..{}, history_weeks={}, z>={}, min_delta={}",
+                check_from_str,
+                check_to_str,
+                thresholds.history_weeks,
+                thresholds.z_threshold,
+                thresholds.min_qty_delta
+            ),
+        )?;
+
+        let anomalies = sales_anomalies::detect(check_from, check_to, &thresholds).await?;
+        let new_anomalies = sales_anomalies::store(
+            &anomalies,
+            &check_from_str,
+            &check_to_str,
+            &now.to_rfc3339(),
+        )
+        .await?;
+
+        logger.write_log(
+            session_id,
+            &format!(
+                "Аномалий: {} (новых: {})",
+                anomalies.len(),
+                new_anomalies.len()
+            ),
+        )?;
+
+        if new_anomalies.is_empty() {
+            return Ok(TaskRunOutcome::completed());
+        }
+
+        let notification = Notification {
+            event: NotificationEvent::AlertFired,
+            title: format!("Аномалии продаж: {} новых", new_anomalies.len()),
+            body: sales_anomalies::notification_text(&new_anomalies, NOTIFICATION_LINES),
+            tab_key: Some("d411_sales_anomalies".to_string()),
+            link: None,
+        };
+        let report =
+            dispatcher::dispatch(&notification, &dispatcher::active_user_ids(false).await?).await;
+        logger.write_log(
+            session_id,
+            &format!(
+                "Уведомления пользователям: в приложении {}, email {}, Telegram {}, ошибок {}",
+                report.in_app, report.email, report.telegram, report.failed
+            ),
+        )?;
+
+        if report.failed > 0 {
+            Ok(TaskRunOutcome::completed_with_errors())
+        } else {
+            Ok(TaskRunOutcome::completed())
+        }
+    }
+
+    fn get_progress(&self, _session_id: &str) -> Option<TaskProgress> {
+        None
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use contracts::system::tasks::aggregate::ScheduledTask;
+use contracts::system::tasks::metadata::{
+    TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+};
+use contracts::system::tasks::progress::TaskProgress;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::a007_marketplace_product::demand_forecast::{self, ForecastParams};
+use crate::system::tasks::logger::TaskLogger;
+use crate::system::tasks::manager::{TaskManager, TaskRunOutcome};
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    #[serde(default = "default_history_weeks")]
+    history_weeks: i64,
+    #[serde(default = "default_horizon_weeks")]
+    horizon_weeks: i64,
+    #[serde(default = "default_alpha_percent")]
+    alpha_percent: i64,
+}
+
+fn default_history_weeks() -> i64 {
+    104
+}
+
+fn default_horizon_weeks() -> i64 {
+    12
+}
+
+fn default_alpha_percent() -> i64 {
+    30
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            history_weeks: default_history_weeks(),
+            horizon_weeks: default_horizon_weeks(),
+            alpha_percent: default_alpha_percent(),
+        }
+    }
+}
+
+impl Config {
+    fn params(&self) -> ForecastParams {
+        ForecastParams {
+            alpha: self.alpha_percent.clamp(1, 100) as f64 / 100.0,
+            ..ForecastParams::default()
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Metadata
+// ---------------------------------------------------------------------------
+
+static METADATA: TaskMetadata = TaskMetadata {
+    task_type: "task029_demand_forecast",
+    write_tables: &["a007_demand_forecasts"],
+    display_name: "Аналитика — прогноз спроса",
+    description: "По регистру продаж p900 строит недельный прогноз продаж в штуках по каждому \
+        товару: экспоненциальное сглаживание, при истории от года — с помесячной сезонностью, \
+        у коротких рядов — среднее. Прогнозы каждого запуска сохраняются, дашборд «Прогноз \
+        спроса» сравнивает их с фактом.",
+    external_apis: &[],
+    constraints: &[
+        "Запускать раз в неделю после закрытия недели продаж",
+        "Повторный запуск в ту же неделю заменяет её прогноз",
+        "Учитываются только завершённые недели; текущая неделя в историю не попадает",
+    ],
+    config_fields: &[
+        TaskConfigField {
+            key: "history_weeks",
+            label: "История, недель",
+            hint: "Сколько завершённых недель продаж брать для прогноза",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("104"),
+            min_value: Some(4),
+            max_value: Some(260),
+        },
+        TaskConfigField {
+            key: "horizon_weeks",
+            label: "Горизонт, недель",
+            hint: "На сколько недель вперёд строить прогноз",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("12"),
+            min_value: Some(1),
+            max_value: Some(52),
+        },
+        TaskConfigField {
+            key: "alpha_percent",
+            label: "Коэффициент сглаживания, %",
+            hint: "Больше — прогноз быстрее следует за последними неделями",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("30"),
+            min_value: Some(1),
+            max_value: Some(100),
+        },
+    ],
+    concurrency_class: TaskConcurrencyClass::Analytics,
+    max_duration_seconds: 1800,
+};
+
+pub struct Task029DemandForecastManager;
+
+impl Task029DemandForecastManager {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl TaskManager for Task029DemandForecastManager {
+    fn task_type(&self) -> &'static str {
+        "task029_demand_forecast"
+    }
+
+    fn metadata(&self) -> &'static TaskMetadata {
+        &METADATA
+    }
+
+    async fn run(
+        &self,
+        task: &ScheduledTask,
+        session_id: &str,
+        logger: Arc<TaskLogger>,
+    ) -> Result<TaskRunOutcome> {
+        let config: Config = serde_json::from_str(&task.config_json).unwrap_or_default();
+        let params = config.params();
+        let history_weeks = config.history_weeks.clamp(4, 260);
+        let horizon = config.horizon_weeks.clamp(1, 52) as usize;
+        let now = chrono::Utc::now();
+        let made_on = demand_forecast::week_start(now.date_naive());
+
+        logger.write_log(
+            session_id,
+            &format!(
+                "Demand forecast started: made_on={}, history_weeks={}, horizon_weeks={}, alpha={}",
+                made_on, history_weeks, horizon, params.alpha
+            ),
+        )?;
+
+        let rows = demand_forecast::compute(made_on, history_weeks, horizon, &params).await?;
+        demand_forecast::replace_run(made_on, &rows, &now.to_rfc3339()).await?;
+
+        let mut methods: HashMap<&'static str, usize> = HashMap::new();
+        for row in rows.iter().filter(|row| row.week_start == made_on) {
+            *methods.entry(row.method.label()).or_default() += 1;
+        }
+        let mut methods: Vec<String> = methods
+            .into_iter()
+            .map(|(label, count)| format!("{label}: {count}"))
+            .collect();
+        methods.sort();
+        logger.write_log(
+            session_id,
+            &format!(
+                "Прогноз построен: строк {}, товаров по методам — {}",
+                rows.len(),
+                if methods.is_empty() {
+                    "нет".to_string()
+                } else {
+                    methods.join(", ")
+                }
+            ),
+        )?;
+
+        Ok(TaskRunOutcome::completed())
+    }
+
+    fn get_progress(&self, _session_id: &str) -> Option<TaskProgress> {
+        None
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use contracts::system::tasks::aggregate::ScheduledTask;
+use contracts::system::tasks::metadata::{
+    TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+};
+use contracts::system::tasks::progress::TaskProgress;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::system::s3::ocr;
+use crate::system::tasks::logger::TaskLogger;
+use crate::system::tasks::manager::{TaskManager, TaskRunOutcome};
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    #[serde(default = "default_batch_size")]
+    batch_size: i64,
+    #[serde(default)]
+    retry_failed: i64,
+}
+
+fn default_batch_size() -> i64 {
+    20
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            batch_size: default_batch_size(),
+            retry_failed: 0,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Metadata
+// ---------------------------------------------------------------------------
+
+static METADATA: TaskMetadata = TaskMetadata {
+    task_type: "task030_attachment_ocr",
+    write_tables: &["sys_file_ocr", "sys_file_ocr_fts"],
+    display_name: "Вложения — распознавание текста",
+    description: "Извлекает текст из новых файлов S3 категории «Документы» (сканы актов, \
+        счета) и добавляет его в полнотекстовый индекс: поиск по тексту вложений находит \
+        документ, к которому файл приложен. Движок распознавания задаётся в config.toml [ocr].",
+    external_apis: &[],
+    constraints: &[
+        "При [ocr].engine = \"none\" распознаются только текстовые файлы, остальные пропускаются",
+        "Для сканов нужны tesseract и pdftotext на сервере; у PDF читается только текстовый слой",
+        "Каждый файл обрабатывается один раз; упавшие — повторно при retry_failed = 1 (до 3 попыток)",
+    ],
+    config_fields: &[
+        TaskConfigField {
+            key: "batch_size",
+            label: "Файлов за запуск",
+            hint: "Сколько файлов распознавать за один запуск",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("20"),
+            min_value: Some(1),
+            max_value: Some(500),
+        },
+        TaskConfigField {
+            key: "retry_failed",
+            label: "Повторять ошибки",
+            hint: "1 — повторно распознавать файлы, завершившиеся ошибкой",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("0"),
+            min_value: Some(0),
+            max_value: Some(1),
+        },
+    ],
+    concurrency_class: TaskConcurrencyClass::Communication,
+    max_duration_seconds: 1800,
+};
+
+pub struct Task030AttachmentOcrManager;
+
+impl Task030AttachmentOcrManager {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl TaskManager for Task030AttachmentOcrManager {
+    fn task_type(&self) -> &'static str {
+        "task030_attachment_ocr"
+    }
+
+    fn metadata(&self) -> &'static TaskMetadata {
+        &METADATA
+    }
+
+    async fn run(
+        &self,
+        task: &ScheduledTask,
+        session_id: &str,
+        logger: Arc<TaskLogger>,
+    ) -> Result<TaskRunOutcome> {
+        let config: Config = serde_json::from_str(&task.config_json).unwrap_or_default();
+        let batch_size = config.batch_size.clamp(1, 500) as u64;
+        let retry_failed = config.retry_failed != 0;
+
+        logger.write_log(
+            session_id,
+            &format!(
+                "Attachment OCR started: batch_size={}, retry_failed={}",
+                batch_size, retry_failed
+            ),
+        )?;
+
+        let report = ocr::process_pending(batch_size, retry_failed).await?;
+        for error in &report.errors {
+            logger.write_log(session_id, &format!("OCR error: {}", error))?;
+        }
+        logger.write_log(
+            session_id,
+            &format!(
+                "Обработано файлов: {}, в индексе: {}, пропущено: {}, ошибок: {}",
+                report.processed, report.indexed, report.skipped, report.failed
+            ),
+        )?;
+
+        if report.failed > 0 {
+            Ok(TaskRunOutcome::completed_with_errors())
+        } else {
+            Ok(TaskRunOutcome::completed())
+        }
+    }
+
+    fn get_progress(&self, _session_id: &str) -> Option<TaskProgress> {
+        None
+    }
+}
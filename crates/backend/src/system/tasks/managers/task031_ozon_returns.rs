@@ -0,0 +1,215 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use contracts::system::tasks::aggregate::ScheduledTask;
+use contracts::system::tasks::metadata::{
+    ExternalApiInfo, TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+};
+use contracts::system::tasks::progress::TaskProgress;
+use contracts::usecases::u502_import_from_ozon::progress::ImportStatus;
+use contracts::usecases::u502_import_from_ozon::request::{ImportMode, ImportRequest};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::system::tasks::logger::TaskLogger;
+use crate::system::tasks::manager::{TaskManager, TaskRunOutcome};
+use crate::usecases::u502_import_from_ozon::ImportExecutor;
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+fn default_work_start_date() -> String {
+    "2026-01-01".to_string()
+}
+fn default_overlap_days() -> i64 {
+    2
+}
+fn default_chunk_days() -> i64 {
+    14
+}
+
+#[derive(Deserialize)]
+struct Config {
+    connection_id: String,
+    #[serde(default = "default_work_start_date")]
+    work_start_date: String,
+    #[serde(default = "default_overlap_days")]
+    overlap_days: i64,
+    #[serde(default = "default_chunk_days")]
+    chunk_days: i64,
+}
+
+// ---------------------------------------------------------------------------
+// Metadata
+// ---------------------------------------------------------------------------
+
+static METADATA: TaskMetadata = TaskMetadata {
+    task_type: "task031_ozon_returns",
+    write_tables: &["a009_ozon_returns"],
+    display_name: "OZON Возвраты",
+    description: "Загружает возвраты OZON через Seller API (POST /v1/returns/list) \
+        с фильтром по дате логистического возврата. Окно загрузки управляется watermark \
+        от последней успешной загрузки: грузит порциями chunk_days дней с перекрытием \
+        overlap_days и догоняет до сегодня. Повторная загрузка перекрывающихся окон безопасна: \
+        возврат находится по return_id и обновляется. Данные сохраняются в a009_ozon_returns.",
+    external_apis: &[ExternalApiInfo {
+        name: "OZON Seller API",
+        base_url: "https://api-seller.ozon.ru/",
+        rate_limit_desc: "До 10 000 запросов/час на метод; пагинация по last_id, до 500 записей",
+    }],
+    constraints: &[
+        "Требует Client-Id и Api-Key в конфигурации подключения (connection_id)",
+        "overlap_days (по умолчанию 2) компенсирует поздно зарегистрированные возвраты",
+        "chunk_days (по умолчанию 14) — максимальный диапазон за один запуск",
+        "Сброс watermark в UI запускает догон с work_start_date порциями chunk_days",
+        "Рекомендуется запускать 1 раз в день",
+    ],
+    config_fields: &[
+        TaskConfigField {
+            key: "connection_id",
+            label: "Кабинет OZON",
+            hint: "Подключение к OZON Seller API из справочника «Подключения маркетплейсов»",
+            field_type: TaskConfigFieldType::ConnectionMp,
+            required: true,
+            default_value: None,
+            min_value: None,
+            max_value: None,
+        },
+        TaskConfigField {
+            key: "work_start_date",
+            label: "Дата начала работы",
+            hint: "Начиная с этой даты данные должны быть загружены полностью",
+            field_type: TaskConfigFieldType::Date,
+            required: false,
+            default_value: Some("2026-01-01"),
+            min_value: None,
+            max_value: None,
+        },
+        TaskConfigField {
+            key: "overlap_days",
+            label: "Перекрытие от watermark (дн)",
+            hint: "Запас назад от watermark для надёжности границ и обновлений статусов",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("2"),
+            min_value: Some(0),
+            max_value: Some(14),
+        },
+        TaskConfigField {
+            key: "chunk_days",
+            label: "Размер порции (дн)",
+            hint: "Максимальный диапазон за один запуск при догоняющей загрузке",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("14"),
+            min_value: Some(1),
+            max_value: Some(90),
+        },
+    ],
+    concurrency_class: TaskConcurrencyClass::MarketplaceImport,
+    max_duration_seconds: 3600,
+};
+
+// ---------------------------------------------------------------------------
+// Manager
+// ---------------------------------------------------------------------------
+
+/// Регламентное задание загрузки возвратов OZON (task031).
+/// Watermark-стратегия по образцу task018 YM; запускает u502 только для a009_ozon_returns.
+pub struct Task031OzonReturnsManager {
+    executor: Arc<ImportExecutor>,
+}
+
+impl Task031OzonReturnsManager {
+    pub fn new(executor: Arc<ImportExecutor>) -> Self {
+        Self { executor }
+    }
+}
+
+#[async_trait]
+impl TaskManager for Task031OzonReturnsManager {
+    fn task_type(&self) -> &'static str {
+        "task031_ozon_returns"
+    }
+
+    fn metadata(&self) -> &'static TaskMetadata {
+        &METADATA
+    }
+
+    async fn run(
+        &self,
+        task: &ScheduledTask,
+        session_id: &str,
+        logger: Arc<TaskLogger>,
+    ) -> Result<TaskRunOutcome> {
+        let cfg: Config = serde_json::from_str(&task.config_json)
+            .context("Config parse failed — expected {\"connection_id\":\"<uuid>\",\"work_start_date\":\"2026-01-01\",\"overlap_days\":2,\"chunk_days\":14}")?;
+
+        let connection_id = super::config_helpers::parse_connection_id(&cfg.connection_id, "Ozon")?;
+        let connection = crate::domain::a006_connection_mp::service::get_by_id(connection_id)
+            .await?
+            .ok_or_else(|| {
+                anyhow::anyhow!("Marketplace connection not found: {}", connection_id)
+            })?;
+
+        let (date_from, date_to) = super::config_helpers::compute_date_window(
+            task,
+            &cfg.work_start_date,
+            cfg.overlap_days,
+            cfg.chunk_days,
+        );
+
+        logger.write_log(
+            session_id,
+            &format!(
+                "task031 OZON Returns: {} → {}; connection_id={}",
+                date_from, date_to, cfg.connection_id
+            ),
+        )?;
+
+        let req = ImportRequest {
+            connection_id: cfg.connection_id,
+            target_aggregates: vec!["a009_ozon_returns".to_string()],
+            date_from,
+            date_to,
+            mode: ImportMode::Background,
+        };
+
+        self.executor
+            .execute_import(session_id, &req, &connection)
+            .await?;
+
+        let completed_with_errors = self
+            .executor
+            .get_progress(session_id)
+            .map(|p| {
+                p.total_errors > 0
+                    || matches!(
+                        p.status,
+                        ImportStatus::CompletedWithErrors | ImportStatus::Failed
+                    )
+            })
+            .unwrap_or(false);
+        if completed_with_errors {
+            logger.write_log(
+                session_id,
+                "task031 completed with errors; watermark NOT advanced — see progress/errors.",
+            )?;
+            return Ok(TaskRunOutcome::completed_with_errors());
+        }
+
+        logger.write_log(session_id, "task031: OZON Returns completed")?;
+        Ok(TaskRunOutcome::completed_loaded_to(date_to))
+    }
+
+    fn get_progress(&self, session_id: &str) -> Option<TaskProgress> {
+        self.executor
+            .progress_tracker
+            .get_progress(session_id)
+            .map(|p| p.into())
+    }
+
+    fn list_live_progress_sessions(&self) -> Vec<TaskProgress> {
+        self.executor.list_live_task_progress()
+    }
+}
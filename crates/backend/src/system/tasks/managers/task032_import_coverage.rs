@@ -0,0 +1,250 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use contracts::dashboards::d414_import_coverage::ImportCoverageRequest;
+use contracts::system::notifications::NotificationEvent;
+use contracts::system::tasks::aggregate::ScheduledTask;
+use contracts::system::tasks::metadata::{
+    ExternalApiInfo, TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+};
+use contracts::system::tasks::progress::TaskProgress;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::dashboards::d414_import_coverage::{repository, service};
+use crate::system::notifications::dispatcher::{self, Notification};
+use crate::system::tasks::logger::TaskLogger;
+use crate::system::tasks::manager::{TaskManager, TaskRunOutcome};
+
+/// Сколько расхождений перечислять в тексте уведомления.
+const NOTIFICATION_LINES: usize = 20;
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    /// Пусто — все используемые кабинеты WB и Ozon.
+    #[serde(default)]
+    connection_id: String,
+    #[serde(default = "default_check_days")]
+    check_days: i64,
+    #[serde(default = "default_lag_days")]
+    lag_days: i64,
+}
+
+fn default_check_days() -> i64 {
+    7
+}
+
+fn default_lag_days() -> i64 {
+    1
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            connection_id: String::new(),
+            check_days: default_check_days(),
+            lag_days: default_lag_days(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Metadata
+// ---------------------------------------------------------------------------
+
+static METADATA: TaskMetadata = TaskMetadata {
+    task_type: "task032_import_coverage",
+    write_tables: &["d414_import_claims"],
+    display_name: "Контроль — покрытие импорта по дням",
+    description: "Запрашивает у маркетплейсов сводки за последние дни — WB: отчёт продаж \
+        Statistics API, Ozon: число транзакций и итоги /v3/finance/transaction/totals — и \
+        сравнивает их с загруженными документами a012/a014. Результат виден в отчёте \
+        «Покрытие импорта»; о пропущенных и недогруженных днях уведомляются пользователи \
+        (событие «Сработал алерт»).",
+    external_apis: &[
+        ExternalApiInfo {
+            name: "WB Statistics API",
+            base_url: "https://statistics-api.wildberries.ru/",
+            rate_limit_desc: "Один запрос /api/v1/supplier/sales на кабинет за запуск",
+        },
+        ExternalApiInfo {
+            name: "OZON Seller API",
+            base_url: "https://api-seller.ozon.ru/",
+            rate_limit_desc: "Два запроса на каждый проверяемый день кабинета",
+        },
+    ],
+    constraints: &[
+        "Запускать после ежедневных импортов продаж WB и транзакций Ozon",
+        "Сводка дня перезаписывается при каждой проверке: последние дни маркетплейс ещё дополняет",
+        "Ошибка API одного кабинета не останавливает проверку остальных",
+    ],
+    config_fields: &[
+        TaskConfigField {
+            key: "connection_id",
+            label: "Кабинет",
+            hint: "Пусто — все используемые кабинеты WB и Ozon",
+            field_type: TaskConfigFieldType::ConnectionMp,
+            required: false,
+            default_value: None,
+            min_value: None,
+            max_value: None,
+        },
+        TaskConfigField {
+            key: "check_days",
+            label: "Проверять дней",
+            hint: "Сколько последних дней сверять за запуск",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("7"),
+            min_value: Some(1),
+            max_value: Some(31),
+        },
+        TaskConfigField {
+            key: "lag_days",
+            label: "Отставание данных, дней",
+            hint: "Последние дни, которые маркетплейс ещё не закрыл, не проверяются",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("1"),
+            min_value: Some(0),
+            max_value: Some(14),
+        },
+    ],
+    concurrency_class: TaskConcurrencyClass::MarketplaceImport,
+    max_duration_seconds: 1800,
+};
+
+pub struct Task032ImportCoverageManager;
+
+impl Task032ImportCoverageManager {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl TaskManager for Task032ImportCoverageManager {
+    fn task_type(&self) -> &'static str {
+        "task032_import_coverage"
+    }
+
+    fn metadata(&self) -> &'static TaskMetadata {
+        &METADATA
+    }
+
+    async fn run(
+        &self,
+        task: &ScheduledTask,
+        session_id: &str,
+        logger: Arc<TaskLogger>,
+    ) -> Result<TaskRunOutcome> {
+        let config: Config = serde_json::from_str(&task.config_json).unwrap_or_default();
+        let now = chrono::Utc::now();
+        let check_to = now.date_naive() - chrono::Duration::days(config.lag_days.clamp(0, 14));
+        let check_from = check_to - chrono::Duration::days(config.check_days.clamp(1, 31) - 1);
+        let checked_at = now.to_rfc3339();
+        let only = Some(config.connection_id.trim()).filter(|id| !id.is_empty());
+
+        logger.write_log(
+            session_id,
+            &format!("Import coverage started: days {}..{}", check_from, check_to),
+        )?;
+
+        let mut failed = 0usize;
+        for connection in repository::list_connections(only, None).await? {
+            let Some(source) = service::source_for(&connection) else {
+                continue;
+            };
+            let claims = async {
+                let uuid = uuid::Uuid::parse_str(&connection.id)?;
+                let full = crate::domain::a006_connection_mp::service::get_by_id(uuid)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("connection not found"))?;
+                service::fetch_claims(&full, source, check_from, check_to, &checked_at).await
+            }
+            .await;
+            match claims {
+                Ok(claims) => {
+                    repository::upsert_claims(&claims).await?;
+                    logger.write_log(
+                        session_id,
+                        &format!(
+                            "{} ({}): сводок за {} дн.",
+                            connection.description,
+                            source.label(),
+                            claims.len()
+                        ),
+                    )?;
+                }
+                Err(error) => {
+                    failed += 1;
+                    logger.write_log(
+                        session_id,
+                        &format!(
+                            "{} ({}): ошибка API — {}",
+                            connection.description,
+                            source.label(),
+                            error
+                        ),
+                    )?;
+                }
+            }
+        }
+
+        let report = service::build_report(
+            ImportCoverageRequest {
+                date_from: check_from.format("%Y-%m-%d").to_string(),
+                date_to: check_to.format("%Y-%m-%d").to_string(),
+                connection_mp_ref: only.map(str::to_string),
+                problems_only: Some(true),
+            },
+            check_from,
+            check_to,
+        )
+        .await?;
+        logger.write_log(
+            session_id,
+            &format!(
+                "Расхождений: {} (дней с пропусками: {})",
+                report.problem_rows, report.gap_days
+            ),
+        )?;
+
+        if !report.rows.is_empty() {
+            let notification = Notification {
+                event: NotificationEvent::AlertFired,
+                title: format!(
+                    "Покрытие импорта: расхождений {}, дней с пропусками {}",
+                    report.problem_rows, report.gap_days
+                ),
+                body: service::notification_text(&report.rows, NOTIFICATION_LINES),
+                tab_key: Some("d414_import_coverage".to_string()),
+                link: None,
+            };
+            let sent =
+                dispatcher::dispatch(&notification, &dispatcher::active_user_ids(false).await?)
+                    .await;
+            logger.write_log(
+                session_id,
+                &format!(
+                    "Уведомления пользователям: в приложении {}, email {}, Telegram {}, ошибок {}",
+                    sent.in_app, sent.email, sent.telegram, sent.failed
+                ),
+            )?;
+            failed += sent.failed;
+        }
+
+        if failed > 0 {
+            Ok(TaskRunOutcome::completed_with_errors())
+        } else {
+            Ok(TaskRunOutcome::completed())
+        }
+    }
+
+    fn get_progress(&self, _session_id: &str) -> Option<TaskProgress> {
+        None
+    }
+}
@@ -0,0 +1,198 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use contracts::system::tasks::aggregate::ScheduledTask;
+use contracts::system::tasks::metadata::{
+    ExternalApiInfo, TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+};
+use contracts::system::tasks::progress::TaskProgress;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::system::tasks::logger::TaskLogger;
+use crate::system::tasks::manager::{TaskManager, TaskRunOutcome};
+use crate::usecases::u510_sync_ozon_fbs_status::sync::{self, SyncWindow};
+
+/// Сколько ошибок по документам выводить в лог сессии.
+const LOGGED_ERRORS: usize = 20;
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+fn default_overlap_days() -> i64 {
+    1
+}
+fn default_lookback_days() -> i64 {
+    60
+}
+
+#[derive(Deserialize)]
+struct Config {
+    connection_id: String,
+    #[serde(default = "default_overlap_days")]
+    overlap_days: i64,
+    #[serde(default = "default_lookback_days")]
+    lookback_days: i64,
+}
+
+// ---------------------------------------------------------------------------
+// Metadata
+// ---------------------------------------------------------------------------
+
+static METADATA: TaskMetadata = TaskMetadata {
+    task_type: "task033_ozon_fbs_status_sync",
+    write_tables: &["a010_ozon_fbs_posting", "u510_fbs_status_log"],
+    display_name: "OZON FBS — синхронизация статусов",
+    description: "Запрашивает у OZON Seller API (POST /v3/posting/fbs/list с фильтром \
+        last_changed_status_date) отправления FBS, сменившие статус с последней синхронизации, \
+        и обновляет статус, подстатус и дату доставки загруженных документов a010. Изменённые \
+        документы перепроводятся, каждая смена пишется в журнал u510_fbs_status_log. \
+        Отправления, ещё не загруженные импортом u502, пропускаются.",
+    external_apis: &[ExternalApiInfo {
+        name: "OZON Seller API",
+        base_url: "https://api-seller.ozon.ru/",
+        rate_limit_desc: "Пагинация по offset, до 1000 отправлений на страницу",
+    }],
+    constraints: &[
+        "Требует Client-Id и Api-Key в конфигурации подключения (connection_id)",
+        "Окно смены статуса начинается от watermark минус overlap_days (по умолчанию 1)",
+        "lookback_days (по умолчанию 60) ограничивает окно после сброса watermark \
+         и задаёт, насколько старые отправления проверяются",
+        "Повторная обработка перекрытия безопасна: неизменившийся статус не пишется",
+    ],
+    config_fields: &[
+        TaskConfigField {
+            key: "connection_id",
+            label: "Кабинет OZON",
+            hint: "Подключение к OZON Seller API из справочника «Подключения маркетплейсов»",
+            field_type: TaskConfigFieldType::ConnectionMp,
+            required: true,
+            default_value: None,
+            min_value: None,
+            max_value: None,
+        },
+        TaskConfigField {
+            key: "overlap_days",
+            label: "Перекрытие от watermark (дн)",
+            hint: "Запас назад от последней синхронизации для надёжности границ",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("1"),
+            min_value: Some(0),
+            max_value: Some(7),
+        },
+        TaskConfigField {
+            key: "lookback_days",
+            label: "Глубина проверки (дн)",
+            hint: "Максимальное окно смены статуса и возраст проверяемых отправлений",
+            field_type: TaskConfigFieldType::Integer,
+            required: false,
+            default_value: Some("60"),
+            min_value: Some(7),
+            max_value: Some(180),
+        },
+    ],
+    concurrency_class: TaskConcurrencyClass::MarketplaceImport,
+    max_duration_seconds: 1800,
+};
+
+// ---------------------------------------------------------------------------
+// Manager
+// ---------------------------------------------------------------------------
+
+/// Регламентное задание синхронизации статусов OZON FBS отправлений (task033, u510).
+pub struct Task033OzonFbsStatusSyncManager;
+
+impl Task033OzonFbsStatusSyncManager {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl TaskManager for Task033OzonFbsStatusSyncManager {
+    fn task_type(&self) -> &'static str {
+        "task033_ozon_fbs_status_sync"
+    }
+
+    fn metadata(&self) -> &'static TaskMetadata {
+        &METADATA
+    }
+
+    async fn run(
+        &self,
+        task: &ScheduledTask,
+        session_id: &str,
+        logger: Arc<TaskLogger>,
+    ) -> Result<TaskRunOutcome> {
+        let cfg: Config = serde_json::from_str(&task.config_json)
+            .context("Config parse failed — expected {\"connection_id\":\"<uuid>\",\"overlap_days\":1,\"lookback_days\":60}")?;
+
+        let connection_id = super::config_helpers::parse_connection_id(&cfg.connection_id, "Ozon")?;
+        let connection = crate::domain::a006_connection_mp::service::get_by_id(connection_id)
+            .await?
+            .ok_or_else(|| {
+                anyhow::anyhow!("Marketplace connection not found: {}", connection_id)
+            })?;
+
+        let now = Utc::now();
+        let today = now.date_naive();
+        let lookback = Duration::days(cfg.lookback_days.clamp(7, 180));
+        let earliest = today - lookback;
+        let watermark = task
+            .data_loaded_up_to
+            .or_else(|| task.last_successful_run_at.map(|last| last.date_naive()));
+        let changed_from = watermark
+            .map(|date| date - Duration::days(cfg.overlap_days.clamp(0, 7)))
+            .unwrap_or(earliest)
+            .clamp(earliest, today)
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is valid")
+            .and_utc();
+        let window = SyncWindow {
+            created_since: changed_from - lookback,
+            changed_from,
+            changed_to: now,
+        };
+
+        logger.write_log(
+            session_id,
+            &format!(
+                "task033 OZON FBS statuses: changed {} → {}, created since {}; connection_id={}",
+                window.changed_from, window.changed_to, window.created_since, cfg.connection_id
+            ),
+        )?;
+
+        let result = sync::sync_statuses(&connection, window, Some(session_id)).await?;
+
+        logger.write_log(
+            session_id,
+            &format!(
+                "task033: получено {}, обновлено {}, без изменений {}, не загружено импортом {}, ошибок {}",
+                result.fetched,
+                result.updated,
+                result.unchanged,
+                result.not_imported,
+                result.errors.len()
+            ),
+        )?;
+
+        if !result.errors.is_empty() {
+            for error in result.errors.iter().take(LOGGED_ERRORS) {
+                logger.write_log(session_id, error)?;
+            }
+            logger.write_log(
+                session_id,
+                "task033 completed with errors; watermark NOT advanced.",
+            )?;
+            return Ok(TaskRunOutcome::completed_with_errors());
+        }
+
+        Ok(TaskRunOutcome::completed_loaded_to(today))
+    }
+
+    fn get_progress(&self, _session_id: &str) -> Option<TaskProgress> {
+        None
+    }
+}
@@ -0,0 +1,148 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use contracts::system::notifications::NotificationEvent;
+use contracts::system::tasks::aggregate::ScheduledTask;
+use contracts::system::tasks::metadata::{TaskConcurrencyClass, TaskMetadata};
+use contracts::system::tasks::progress::TaskProgress;
+use std::sync::Arc;
+
+use crate::system::notifications::dispatcher::{self, Notification};
+use crate::system::tasks::logger::TaskLogger;
+use crate::system::tasks::manager::{TaskManager, TaskRunOutcome};
+use crate::system::users::service as user_service;
+
+// ---------------------------------------------------------------------------
+// Metadata
+// ---------------------------------------------------------------------------
+
+static METADATA: TaskMetadata = TaskMetadata {
+    task_type: "task034_user_access_expiry",
+    write_tables: &["sys_users", "sys_refresh_tokens"],
+    display_name: "Пользователи — срок доступа",
+    description: "Отключает учётные записи, у которых истёк срок доступа (active_until), и \
+        отзывает их сессии. За неделю до окончания срока напоминает самому пользователю и \
+        администраторам (событие «Истекает срок доступа») — один раз на каждую дату окончания.",
+    external_apis: &[],
+    constraints: &[
+        "Запускать ежедневно: отключение происходит на следующий день после active_until",
+        "После продления срока напоминание придёт заново для новой даты",
+    ],
+    config_fields: &[],
+    concurrency_class: TaskConcurrencyClass::Communication,
+    max_duration_seconds: 300,
+};
+
+pub struct Task034UserAccessExpiryManager;
+
+impl Task034UserAccessExpiryManager {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn display_name(user: &contracts::system::users::User) -> String {
+    match &user.full_name {
+        Some(name) if !name.is_empty() => format!("{} ({})", name, user.username),
+        _ => user.username.clone(),
+    }
+}
+
+#[async_trait]
+impl TaskManager for Task034UserAccessExpiryManager {
+    fn task_type(&self) -> &'static str {
+        "task034_user_access_expiry"
+    }
+
+    fn metadata(&self) -> &'static TaskMetadata {
+        &METADATA
+    }
+
+    async fn run(
+        &self,
+        _task: &ScheduledTask,
+        session_id: &str,
+        logger: Arc<TaskLogger>,
+    ) -> Result<TaskRunOutcome> {
+        let today = chrono::Utc::now().date_naive();
+        let mut failed = 0usize;
+        let admins = dispatcher::active_user_ids(true).await?;
+
+        let deactivated = user_service::deactivate_expired(today).await?;
+        for user in &deactivated {
+            logger.write_log(
+                session_id,
+                &format!(
+                    "Отключён {}: срок доступа до {}",
+                    user.username,
+                    user.active_until.as_deref().unwrap_or("-")
+                ),
+            )?;
+        }
+        if !deactivated.is_empty() {
+            let names: Vec<String> = deactivated.iter().map(display_name).collect();
+            let notification = Notification {
+                event: NotificationEvent::AccessExpiring,
+                title: format!(
+                    "Отключены учётные записи с истёкшим сроком: {}",
+                    deactivated.len()
+                ),
+                body: names.join("\n"),
+                tab_key: Some("sys_users".to_string()),
+                link: None,
+            };
+            failed += dispatcher::dispatch(&notification, &admins).await.failed;
+        }
+
+        let reminders = user_service::take_due_expiry_reminders(today).await?;
+        for (user, days_left) in &reminders {
+            let until = user.active_until.as_deref().unwrap_or_default();
+            let personal = Notification {
+                event: NotificationEvent::AccessExpiring,
+                title: format!("Срок доступа истекает {}", until),
+                body: format!(
+                    "Учётная запись {} будет отключена после {} (осталось дней: {}). \
+                     Для продления обратитесь к администратору.",
+                    user.username, until, days_left
+                ),
+                tab_key: None,
+                link: None,
+            };
+            let to_admins = Notification {
+                event: NotificationEvent::AccessExpiring,
+                title: format!("Истекает доступ: {}", display_name(user)),
+                body: format!("Срок доступа до {} (осталось дней: {}).", until, days_left),
+                tab_key: Some("sys_users".to_string()),
+                link: None,
+            };
+            let admin_ids: Vec<String> = admins
+                .iter()
+                .filter(|id| **id != user.id)
+                .cloned()
+                .collect();
+            failed += dispatcher::dispatch(&personal, std::slice::from_ref(&user.id))
+                .await
+                .failed;
+            failed += dispatcher::dispatch(&to_admins, &admin_ids).await.failed;
+        }
+
+        logger.write_log(
+            session_id,
+            &format!(
+                "Отключено: {}, напоминаний: {}, ошибок отправки: {}",
+                deactivated.len(),
+                reminders.len(),
+                failed
+            ),
+        )?;
+
+        if failed > 0 {
+            Ok(TaskRunOutcome::completed_with_errors())
+        } else {
+            Ok(TaskRunOutcome::completed())
+        }
+    }
+
+    fn get_progress(&self, _session_id: &str) -> Option<TaskProgress> {
+        None
+    }
+}
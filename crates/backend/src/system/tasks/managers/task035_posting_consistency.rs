@@ -0,0 +1,212 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use contracts::system::notifications::NotificationEvent;
+use contracts::system::tasks::aggregate::ScheduledTask;
+use contracts::system::tasks::metadata::{
+    TaskConcurrencyClass, TaskConfigField, TaskConfigFieldType, TaskMetadata,
+};
+use contracts::system::tasks::progress::TaskProgress;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::quality::checks::posting_consistency::{self, InconsistencyKind};
+use crate::quality::checks::registrator_registry;
+use crate::system::notifications::dispatcher::{self, Notification};
+use crate::system::tasks::logger::TaskLogger;
+use crate::system::tasks::manager::{TaskManager, TaskRunOutcome};
+
+/// Сколько документов перечислять в логе и уведомлении.
+const LIST_LINES: usize = 20;
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    /// 0 — только отчёт, без перепроведения.
+    #[serde(default = "default_max_fixes")]
+    max_fixes: i64,
+}
+
+fn default_max_fixes() -> i64 {
+    500
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_fixes: default_max_fixes(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Metadata
+// ---------------------------------------------------------------------------
+
+static METADATA: TaskMetadata = TaskMetadata {
+    task_type: "task035_posting_consistency",
+    write_tables: &[
+        "a009_ozon_returns",
+        "a010_ozon_fbs_posting",
+        "a012_wb_sales",
+        "p900_sales_register",
+        "p904_sales_data",
+        "p909_mp_order_line_turnovers",
+    ],
+    display_name: "Контроль — флаг проведения и проекции",
+    description: "Сверяет флаг is_posted документов a009/a010/a012 с наличием их строк в P900 \
+        (проверка качества «Флаг проведения ↔ строки проекций»). Проведённые документы без \
+        строк перепроводятся; непроведённые документы со строками в проекции остаются в \
+        отчёте проверки, о них уведомляются администраторы (событие «Сработал алерт»).",
+    external_apis: &[],
+    constraints: &[
+        "Запускать после ночных импортов и проведений, когда документы не меняются",
+        "Непроведённые документы со строками проекций не исправляются: нужно решить, провести или отменить проведение",
+        "Ошибка перепроведения одного документа не останавливает остальные",
+    ],
+    config_fields: &[TaskConfigField {
+        key: "max_fixes",
+        label: "Перепроводить не более",
+        hint: "Лимит документов за запуск; 0 — только отчёт",
+        field_type: TaskConfigFieldType::Integer,
+        required: false,
+        default_value: Some("500"),
+        min_value: Some(0),
+        max_value: Some(10000),
+    }],
+    concurrency_class: TaskConcurrencyClass::ProjectionRebuild,
+    max_duration_seconds: 3600,
+};
+
+pub struct Task035PostingConsistencyManager;
+
+impl Task035PostingConsistencyManager {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl TaskManager for Task035PostingConsistencyManager {
+    fn task_type(&self) -> &'static str {
+        "task035_posting_consistency"
+    }
+
+    fn metadata(&self) -> &'static TaskMetadata {
+        &METADATA
+    }
+
+    async fn run(
+        &self,
+        task: &ScheduledTask,
+        session_id: &str,
+        logger: Arc<TaskLogger>,
+    ) -> Result<TaskRunOutcome> {
+        let config: Config = serde_json::from_str(&task.config_json).unwrap_or_default();
+        let max_fixes = config.max_fixes.clamp(0, 10000) as usize;
+
+        let found = posting_consistency::find_inconsistencies().await?;
+        let (trivial, rest): (Vec<_>, Vec<_>) =
+            found.into_iter().partition(|item| item.kind.is_trivial());
+        logger.write_log(
+            session_id,
+            &format!(
+                "Расхождений: проведены без строк P900 — {}, строки без проведения — {}",
+                trivial.len(),
+                rest.len()
+            ),
+        )?;
+
+        let mut fixed = 0usize;
+        let mut fix_failed = 0usize;
+        let mut remaining = rest;
+        for (index, item) in trivial.into_iter().enumerate() {
+            if index >= max_fixes {
+                remaining.push(item);
+                continue;
+            }
+            match registrator_registry::repost_document(item.registrator_type, &item.document_id)
+                .await
+            {
+                Ok(()) => fixed += 1,
+                Err(error) => {
+                    fix_failed += 1;
+                    logger.write_log(
+                        session_id,
+                        &format!(
+                            "{} {}: перепроведение не удалось — {}",
+                            item.registrator_type, item.document_id, error
+                        ),
+                    )?;
+                    remaining.push(item);
+                }
+            }
+        }
+        logger.write_log(
+            session_id,
+            &format!(
+                "Перепроведено: {}, ошибок: {}, осталось расхождений: {}",
+                fixed,
+                fix_failed,
+                remaining.len()
+            ),
+        )?;
+
+        let mut failed = fix_failed;
+        if !remaining.is_empty() {
+            let lines: Vec<String> = remaining
+                .iter()
+                .take(LIST_LINES)
+                .map(|item| {
+                    let reason = match item.kind {
+                        InconsistencyKind::PostedWithoutProjection => "проведён, строк P900 нет",
+                        InconsistencyKind::ProjectionWithoutPosting => {
+                            "не проведён, строки P900 есть"
+                        }
+                    };
+                    format!("{} {}: {}", item.registrator_type, item.document_id, reason)
+                })
+                .collect();
+            for line in &lines {
+                logger.write_log(session_id, line)?;
+            }
+
+            let notification = Notification {
+                event: NotificationEvent::AlertFired,
+                title: format!(
+                    "Флаг проведения расходится с проекциями: {} документов",
+                    remaining.len()
+                ),
+                body: lines.join("\n"),
+                tab_key: Some(format!(
+                    "quality_check_details_{}",
+                    posting_consistency::CHECK_ID
+                )),
+                link: None,
+            };
+            let sent =
+                dispatcher::dispatch(&notification, &dispatcher::active_user_ids(true).await?)
+                    .await;
+            logger.write_log(
+                session_id,
+                &format!(
+                    "Уведомления администраторам: в приложении {}, email {}, Telegram {}, ошибок {}",
+                    sent.in_app, sent.email, sent.telegram, sent.failed
+                ),
+            )?;
+            failed += sent.failed;
+        }
+
+        if failed > 0 {
+            Ok(TaskRunOutcome::completed_with_errors())
+        } else {
+            Ok(TaskRunOutcome::completed())
+        }
+    }
+
+    fn get_progress(&self, _session_id: &str) -> Option<TaskProgress> {
+        None
+    }
+}
@@ -7,8 +7,10 @@ pub mod managers;
 pub mod registry;
 pub mod repository;
 pub mod resource_coordinator;
+pub mod run_summary;
 pub mod runs_repository;
 pub mod runs_service;
+pub mod schedule;
 pub mod service;
 pub mod task_session_runner;
 pub mod worker;
@@ -1,3 +1,5 @@
+use chrono::{DateTime, Utc};
+use contracts::system::tasks::metadata::TaskConcurrencyClass;
 use std::collections::HashMap;
 use std::sync::{Arc, LazyLock, Mutex};
 
@@ -15,6 +17,19 @@ pub struct ResourceConflict {
 struct ResourceOwner {
     session_id: String,
     task: String,
+    acquired_at: DateTime<Utc>,
+}
+
+/// Занятый слот класса параллельности (для панели «Активные»).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassSlotOwner {
+    pub session_id: String,
+    pub task: String,
+    pub acquired_at: DateTime<Utc>,
+}
+
+fn class_slot_key(class: TaskConcurrencyClass, slot: usize) -> String {
+    format!("class:{}#{slot}", class.code())
 }
 
 #[derive(Debug, Default)]
@@ -38,6 +53,7 @@ impl TaskResourceCoordinator {
         task_label: &str,
         session_id: &str,
         write_tables: &[&str],
+        class: TaskConcurrencyClass,
     ) -> Result<TaskResourceGuard, ResourceConflict> {
         // The synthetic task resource closes the race between scheduled and manual starts of
         // the same task, including tasks which do not write database tables.
@@ -61,12 +77,39 @@ impl TaskResourceCoordinator {
             }
         }
 
+        // Класс параллельности — `max_concurrent` слотов; занимаем первый свободный.
+        let Some(slot_key) = (0..class.max_concurrent())
+            .map(|slot| class_slot_key(class, slot))
+            .find(|key| !owners.contains_key(key))
+        else {
+            let owner = owners
+                .get(&class_slot_key(class, 0))
+                .cloned()
+                .unwrap_or_else(|| ResourceOwner {
+                    session_id: String::new(),
+                    task: String::new(),
+                    acquired_at: Utc::now(),
+                });
+            return Err(ResourceConflict {
+                resource: format!(
+                    "класс «{}» (не более {})",
+                    class.label(),
+                    class.max_concurrent()
+                ),
+                owner_session_id: owner.session_id,
+                owner_task: owner.task,
+            });
+        };
+        resources.push((slot_key, class.label().to_string()));
+
+        let acquired_at = Utc::now();
         for (key, _) in &resources {
             owners.insert(
                 key.clone(),
                 ResourceOwner {
                     session_id: session_id.to_string(),
                     task: task_label.to_string(),
+                    acquired_at,
                 },
             );
         }
@@ -77,6 +120,30 @@ impl TaskResourceCoordinator {
             session_id: session_id.to_string(),
         })
     }
+
+    /// Занятые слоты по классам параллельности, в порядке захвата.
+    pub fn running_by_class(&self) -> HashMap<TaskConcurrencyClass, Vec<ClassSlotOwner>> {
+        let owners = self.owners.lock().unwrap_or_else(|e| e.into_inner());
+        let mut result: HashMap<TaskConcurrencyClass, Vec<ClassSlotOwner>> = HashMap::new();
+        for (key, owner) in owners.iter() {
+            let Some(class) = key
+                .strip_prefix("class:")
+                .and_then(|rest| rest.split('#').next())
+                .and_then(TaskConcurrencyClass::from_code)
+            else {
+                continue;
+            };
+            result.entry(class).or_default().push(ClassSlotOwner {
+                session_id: owner.session_id.clone(),
+                task: owner.task.clone(),
+                acquired_at: owner.acquired_at,
+            });
+        }
+        for slots in result.values_mut() {
+            slots.sort_by(|a, b| a.acquired_at.cmp(&b.acquired_at));
+        }
+        result
+    }
 }
 
 impl Drop for TaskResourceGuard {
@@ -101,22 +168,30 @@ pub fn get_global_resource_coordinator() -> Arc<TaskResourceCoordinator> {
 mod tests {
     use super::*;
 
+    const CLASS: TaskConcurrencyClass = TaskConcurrencyClass::ProjectionRebuild;
+
     #[test]
     fn overlapping_tables_are_exclusive_and_release_on_drop() {
         let coordinator = TaskResourceCoordinator::default();
         let first = coordinator
-            .try_acquire("task-1", "first", "session-1", &["table_a", "table_b"])
+            .try_acquire(
+                "task-1",
+                "first",
+                "session-1",
+                &["table_a", "table_b"],
+                CLASS,
+            )
             .unwrap();
 
         let conflict = coordinator
-            .try_acquire("task-2", "second", "session-2", &["table_b"])
+            .try_acquire("task-2", "second", "session-2", &["table_b"], CLASS)
             .unwrap_err();
         assert_eq!(conflict.resource, "table_b");
         assert_eq!(conflict.owner_session_id, "session-1");
 
         drop(first);
         coordinator
-            .try_acquire("task-2", "second", "session-2", &["table_b"])
+            .try_acquire("task-2", "second", "session-2", &["table_b"], CLASS)
             .unwrap();
     }
 
@@ -124,10 +199,10 @@ mod tests {
     fn disjoint_tables_can_run_in_parallel() {
         let coordinator = TaskResourceCoordinator::default();
         let _first = coordinator
-            .try_acquire("task-1", "first", "session-1", &["table_a"])
+            .try_acquire("task-1", "first", "session-1", &["table_a"], CLASS)
             .unwrap();
         let _second = coordinator
-            .try_acquire("task-2", "second", "session-2", &["table_b"])
+            .try_acquire("task-2", "second", "session-2", &["table_b"], CLASS)
             .unwrap();
     }
 
@@ -135,11 +210,62 @@ mod tests {
     fn same_task_is_exclusive_even_without_tables() {
         let coordinator = TaskResourceCoordinator::default();
         let _first = coordinator
-            .try_acquire("task-1", "first", "session-1", &[])
+            .try_acquire("task-1", "first", "session-1", &[], CLASS)
             .unwrap();
         let conflict = coordinator
-            .try_acquire("task-1", "first", "session-2", &[])
+            .try_acquire("task-1", "first", "session-2", &[], CLASS)
             .unwrap_err();
         assert_eq!(conflict.resource, "task first");
     }
+
+    #[test]
+    fn concurrency_class_limits_parallel_runs() {
+        let coordinator = TaskResourceCoordinator::default();
+        let finance = TaskConcurrencyClass::FinanceImport;
+        let first = coordinator
+            .try_acquire("task-1", "first", "session-1", &["table_a"], finance)
+            .unwrap();
+        let conflict = coordinator
+            .try_acquire("task-2", "second", "session-2", &["table_b"], finance)
+            .unwrap_err();
+        assert_eq!(conflict.owner_session_id, "session-1");
+
+        // Другой класс не ограничен финансовым слотом
+        let _other = coordinator
+            .try_acquire("task-3", "third", "session-3", &["table_c"], CLASS)
+            .unwrap();
+        let running = coordinator.running_by_class();
+        assert_eq!(running[&finance].len(), 1);
+        assert_eq!(running[&CLASS][0].task, "third");
+
+        drop(first);
+        assert!(!coordinator.running_by_class().contains_key(&finance));
+        coordinator
+            .try_acquire("task-2", "second", "session-2", &["table_b"], finance)
+            .unwrap();
+    }
+
+    #[test]
+    fn class_slots_are_counted_up_to_limit() {
+        let coordinator = TaskResourceCoordinator::default();
+        let limit = CLASS.max_concurrent();
+        let guards: Vec<_> = (0..limit)
+            .map(|i| {
+                coordinator
+                    .try_acquire(
+                        &format!("task-{i}"),
+                        "t",
+                        &format!("session-{i}"),
+                        &[],
+                        CLASS,
+                    )
+                    .unwrap()
+            })
+            .collect();
+        assert!(coordinator
+            .try_acquire("task-x", "t", "session-x", &[], CLASS)
+            .is_err());
+        drop(guards);
+        assert!(coordinator.running_by_class().is_empty());
+    }
 }
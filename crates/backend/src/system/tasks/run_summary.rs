@@ -0,0 +1,342 @@
+//! Письмо-сводка после планового запуска импорта.
+//!
+//! После каждого запуска воркером задачи, которая ходит во внешний API (импорты
+//! WB / Ozon / YM и пр.), подписанные администраторы получают итог: статус,
+//! длительность, новые и изменённые документы по типам, ошибки и ссылку на запуск.
+//! Ручные запуски не рассылаются — их итог запустивший видит на экране.
+//! Подписка хранится в `sys_settings` (`import_summary_subscribers`) и
+//! переключается самим администратором.
+//!
+//! Неудачные запуски дополнительно уходят всем администраторам как событие
+//! «Ошибка импорта» через диспетчер уведомлений — по их личным настройкам каналов.
+
+use contracts::system::notifications::NotificationEvent;
+use contracts::system::tasks::aggregate::ScheduledTask;
+use contracts::system::tasks::metadata::TaskMetadata;
+use contracts::system::tasks::progress::{TaskAggregateSummary, TaskProgress, TaskStatus};
+
+use super::runs_repository;
+use crate::shared::config::get_mail_config;
+use crate::shared::mail;
+use crate::system::notifications::dispatcher::{self, Notification};
+
+/// Сколько сообщений об ошибках выводить в письме; полный список — в логе запуска.
+const MAX_ERRORS_IN_MAIL: usize = 20;
+
+/// Итог одного запуска для письма.
+#[derive(Debug, Clone)]
+pub struct RunSummary {
+    pub task_description: String,
+    pub status: TaskStatus,
+    pub duration_ms: Option<i64>,
+    pub aggregates: Vec<TaskAggregateSummary>,
+    pub total_inserted: Option<i32>,
+    pub total_updated: Option<i32>,
+    pub errors: Vec<String>,
+}
+
+impl RunSummary {
+    pub fn new(
+        task_description: String,
+        status: TaskStatus,
+        duration_ms: Option<i64>,
+        progress: Option<TaskProgress>,
+        error_message: Option<String>,
+    ) -> Self {
+        let (aggregates, total_inserted, total_updated, mut errors) = match progress {
+            Some(p) => (
+                p.aggregates.unwrap_or_default(),
+                p.total_inserted,
+                p.total_updated,
+                p.errors.unwrap_or_default(),
+            ),
+            None => (Vec::new(), None, None, Vec::new()),
+        };
+        if let Some(message) = error_message {
+            errors.insert(0, message);
+        }
+        Self {
+            task_description,
+            status,
+            duration_ms,
+            aggregates,
+            total_inserted,
+            total_updated,
+            errors,
+        }
+    }
+
+    pub fn subject(&self) -> String {
+        format!(
+            "Импорт «{}»: {}",
+            self.task_description,
+            status_label(&self.status)
+        )
+    }
+
+    /// Текст письма; `link` — ссылка на запуск в приложении (если задан base_url).
+    pub fn render_text(&self, link: Option<&str>) -> String {
+        let mut body = format!(
+            "Задание: {}\nСтатус: {}\n",
+            self.task_description,
+            status_label(&self.status)
+        );
+        if let Some(ms) = self.duration_ms {
+            body.push_str(&format!("Длительность: {}\n", format_duration(ms)));
+        }
+
+        let changed: Vec<&TaskAggregateSummary> = self
+            .aggregates
+            .iter()
+            .filter(|a| a.inserted > 0 || a.updated > 0 || a.errors > 0)
+            .collect();
+        if !changed.is_empty() {
+            body.push_str("\nДокументы по типам (новые / изменённые / ошибки):\n");
+            for a in changed {
+                body.push_str(&format!(
+                    "  {} — {} / {} / {}\n",
+                    a.name, a.inserted, a.updated, a.errors
+                ));
+            }
+        } else if self.total_inserted.is_some() || self.total_updated.is_some() {
+            body.push_str(&format!(
+                "\nНовых: {}, изменённых: {}\n",
+                self.total_inserted.unwrap_or(0),
+                self.total_updated.unwrap_or(0)
+            ));
+        } else {
+            body.push_str("\nИзменений в документах нет.\n");
+        }
+
+        if !self.errors.is_empty() {
+            body.push_str(&format!("\nОшибки ({}):\n", self.errors.len()));
+            for error in self.errors.iter().take(MAX_ERRORS_IN_MAIL) {
+                body.push_str(&format!("  - {error}\n"));
+            }
+            if self.errors.len() > MAX_ERRORS_IN_MAIL {
+                body.push_str("  … полный список — в логе запуска\n");
+            }
+        }
+
+        if let Some(link) = link {
+            body.push_str(&format!("\nЗапуск в приложении: {link}\n"));
+        }
+        body
+    }
+}
+
+/// Сводка рассылается только по задачам, которые тянут данные из внешних API.
+pub fn is_import_task(metadata: &TaskMetadata) -> bool {
+    !metadata.external_apis.is_empty()
+}
+
+/// Deep-link на запуск: вкладка `sys_task_run_{task_id}_{session_id}` открывает лог запуска.
+pub fn run_link(base_url: &str, task_id: &str, session_id: &str) -> Option<String> {
+    let base = base_url.trim().trim_end_matches('/');
+    if base.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{base}/?active=sys_task_run_{task_id}_{session_id}"
+    ))
+}
+
+fn status_label(status: &TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Completed => "выполнено",
+        TaskStatus::CompletedWithErrors => "выполнено с ошибками",
+        TaskStatus::Failed => "ошибка",
+        TaskStatus::Cancelled => "отменено",
+        TaskStatus::Pending | TaskStatus::Running => "выполняется",
+    }
+}
+
+fn format_duration(ms: i64) -> String {
+    let secs = ms.max(0) / 1000;
+    if secs < 60 {
+        format!("{secs} с")
+    } else {
+        format!("{} мин {:02} с", secs / 60, secs % 60)
+    }
+}
+
+/// Адреса подписанных активных администраторов.
+async fn subscriber_emails() -> anyhow::Result<Vec<String>> {
+    let ids = crate::system::settings::service::get_import_summary_subscribers().await?;
+    let mut emails = Vec::with_capacity(ids.len());
+    for id in ids {
+        let Some(user) = crate::system::users::repository::get_by_id(&id).await? else {
+            continue;
+        };
+        if !user.is_active || !user.is_admin {
+            continue;
+        }
+        if let Some(email) = user.email.filter(|e| !e.trim().is_empty()) {
+            emails.push(email);
+        }
+    }
+    Ok(emails)
+}
+
+async fn load_summary(
+    task: &ScheduledTask,
+    session_id: &str,
+    status: TaskStatus,
+    progress: Option<TaskProgress>,
+    error_message: Option<String>,
+) -> RunSummary {
+    let duration_ms = runs_repository::find_by_session_id(session_id)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|run| run.duration_ms);
+    RunSummary::new(
+        task.base.description.clone(),
+        status,
+        duration_ms,
+        progress,
+        error_message,
+    )
+}
+
+/// Рассылает сводку запуска подписчикам. Ошибки только логируются: письмо не
+/// должно влиять на статус запуска.
+pub async fn notify_subscribers(
+    task: &ScheduledTask,
+    session_id: &str,
+    status: TaskStatus,
+    progress: Option<TaskProgress>,
+    error_message: Option<String>,
+) {
+    let mail_config = get_mail_config();
+    if !mail_config.enabled {
+        return;
+    }
+    let recipients = match subscriber_emails().await {
+        Ok(r) if !r.is_empty() => r,
+        Ok(_) => return,
+        Err(e) => {
+            tracing::warn!("[run-summary] failed to load subscribers: {e}");
+            return;
+        }
+    };
+
+    let summary = load_summary(task, session_id, status, progress, error_message).await;
+    let task_id = task.base.id.value().to_string();
+    let link = run_link(&mail_config.base_url, &task_id, session_id);
+    let subject = summary.subject();
+    let body = summary.render_text(link.as_deref());
+
+    for recipient in &recipients {
+        if let Err(e) = mail::check_and_record_send() {
+            tracing::warn!("[run-summary] send rate limit reached: {e}");
+            return;
+        }
+        if let Err(e) = mail::send_email(recipient, &subject, &body).await {
+            tracing::warn!("[run-summary] failed to send to {recipient}: {e}");
+        }
+    }
+}
+
+/// Уведомление «Ошибка импорта» администраторам через диспетчер уведомлений:
+/// каждый получает его по каналам из своих настроек. Успешные запуски не уведомляются.
+pub async fn notify_import_failed(
+    task: &ScheduledTask,
+    session_id: &str,
+    status: TaskStatus,
+    progress: Option<TaskProgress>,
+    error_message: Option<String>,
+) {
+    if !matches!(status, TaskStatus::Failed | TaskStatus::CompletedWithErrors) {
+        return;
+    }
+    let admins = match dispatcher::active_user_ids(true).await {
+        Ok(ids) => ids,
+        Err(e) => {
+            tracing::warn!("[run-summary] failed to load admins: {e}");
+            return;
+        }
+    };
+
+    let summary = load_summary(task, session_id, status, progress, error_message).await;
+    let task_id = task.base.id.value().to_string();
+    let link = run_link(&get_mail_config().base_url, &task_id, session_id);
+    let notification = Notification {
+        event: NotificationEvent::ImportFailed,
+        title: summary.subject(),
+        body: summary.render_text(None),
+        tab_key: Some(format!("sys_task_run_{task_id}_{session_id}")),
+        link,
+    };
+    let report = dispatcher::dispatch(&notification, &admins).await;
+    if report.failed > 0 {
+        tracing::warn!(
+            "[run-summary] import failure notification: {} deliveries failed",
+            report.failed
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary() -> RunSummary {
+        RunSummary {
+            task_description: "WB продажи".to_string(),
+            status: TaskStatus::CompletedWithErrors,
+            duration_ms: Some(125_000),
+            aggregates: vec![
+                TaskAggregateSummary {
+                    index: "a012_wb_sales".to_string(),
+                    name: "Продажи WB".to_string(),
+                    inserted: 12,
+                    updated: 3,
+                    errors: 1,
+                },
+                TaskAggregateSummary {
+                    index: "a015_wb_orders".to_string(),
+                    name: "Заказы WB".to_string(),
+                    inserted: 0,
+                    updated: 0,
+                    errors: 0,
+                },
+            ],
+            total_inserted: Some(12),
+            total_updated: Some(3),
+            errors: vec!["srid-1: нет товара".to_string()],
+        }
+    }
+
+    #[test]
+    fn text_lists_changed_types_errors_and_link() {
+        let text = summary().render_text(Some("https://app/?active=x"));
+        assert!(text.contains("Длительность: 2 мин 05 с"));
+        assert!(text.contains("Продажи WB — 12 / 3 / 1"));
+        assert!(!text.contains("Заказы WB"));
+        assert!(text.contains("  - srid-1: нет товара"));
+        assert!(text.ends_with("Запуск в приложении: https://app/?active=x\n"));
+    }
+
+    #[test]
+    fn run_link_requires_base_url() {
+        assert_eq!(run_link("  ", "t", "s"), None);
+        assert_eq!(
+            run_link("https://app/", "t", "s").as_deref(),
+            Some("https://app/?active=sys_task_run_t_s")
+        );
+    }
+
+    #[test]
+    fn failure_message_goes_first() {
+        let s = RunSummary::new(
+            "X".to_string(),
+            TaskStatus::Failed,
+            None,
+            None,
+            Some("timeout".to_string()),
+        );
+        assert_eq!(s.errors, vec!["timeout".to_string()]);
+        assert_eq!(s.subject(), "Импорт «X»: ошибка");
+    }
+}
@@ -29,6 +29,7 @@ pub struct Model {
     pub http_request_count: Option<i64>,
     pub http_bytes_sent: Option<i64>,
     pub http_bytes_received: Option<i64>,
+    pub http_retry_count: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -59,6 +60,7 @@ impl From<Model> for TaskRun {
             http_request_count: m.http_request_count,
             http_bytes_sent: m.http_bytes_sent,
             http_bytes_received: m.http_bytes_received,
+            http_retry_count: m.http_retry_count,
         }
     }
 }
@@ -82,6 +84,7 @@ struct TaskRunJoinRow {
     http_request_count: Option<i64>,
     http_bytes_sent: Option<i64>,
     http_bytes_received: Option<i64>,
+    http_retry_count: Option<i64>,
     task_code: Option<String>,
     task_description: Option<String>,
     task_comment: Option<String>,
@@ -124,6 +127,7 @@ fn join_row_to_task_run(row: TaskRunJoinRow) -> TaskRun {
         http_request_count: row.http_request_count,
         http_bytes_sent: row.http_bytes_sent,
         http_bytes_received: row.http_bytes_received,
+        http_retry_count: row.http_retry_count,
     }
 }
 
@@ -145,6 +149,7 @@ const RUN_JOIN_SELECT: &str = r#"SELECT
   r.http_request_count AS http_request_count,
   r.http_bytes_sent AS http_bytes_sent,
   r.http_bytes_received AS http_bytes_received,
+  r.http_retry_count AS http_retry_count,
   t.code AS task_code,
   t.description AS task_description,
   t.comment AS task_comment
@@ -176,6 +181,7 @@ pub async fn create_run(
         http_request_count: Set(None),
         http_bytes_sent: Set(None),
         http_bytes_received: Set(None),
+        http_retry_count: Set(None),
     };
     active.insert(db).await?;
     Ok(())
@@ -191,6 +197,7 @@ pub async fn finish_run(
     http_request_count: Option<i64>,
     http_bytes_sent: Option<i64>,
     http_bytes_received: Option<i64>,
+    http_retry_count: Option<i64>,
     error_message: Option<String>,
 ) -> Result<(), DbErr> {
     let db = get_connection();
@@ -213,6 +220,7 @@ pub async fn finish_run(
         active.http_request_count = Set(http_request_count);
         active.http_bytes_sent = Set(http_bytes_sent);
         active.http_bytes_received = Set(http_bytes_received);
+        active.http_retry_count = Set(http_retry_count);
         active.error_message = Set(error_message);
         active.update(db).await?;
     }
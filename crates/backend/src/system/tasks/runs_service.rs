@@ -21,6 +21,7 @@ pub struct RunMetrics {
     pub http_request_count: Option<i64>,
     pub http_bytes_sent: Option<i64>,
     pub http_bytes_received: Option<i64>,
+    pub http_retry_count: Option<i64>,
 }
 
 pub async fn reset_stale_running_runs(reason: &str) -> Result<u64> {
@@ -75,7 +76,7 @@ pub async fn finish_run(
     metrics: Option<RunMetrics>,
     error_message: Option<String>,
 ) -> Result<()> {
-    let (processed, inserted, updated, errors, http_n, http_up, http_down) = metrics
+    let (processed, inserted, updated, errors, http_n, http_up, http_down, http_retries) = metrics
         .map(|m| {
             (
                 m.total_processed,
@@ -85,9 +86,10 @@ pub async fn finish_run(
                 m.http_request_count,
                 m.http_bytes_sent,
                 m.http_bytes_received,
+                m.http_retry_count,
             )
         })
-        .unwrap_or((None, None, None, None, None, None, None));
+        .unwrap_or((None, None, None, None, None, None, None, None));
 
     runs_repository::finish_run(
         session_id,
@@ -99,6 +101,7 @@ pub async fn finish_run(
         http_n,
         http_up,
         http_down,
+        http_retries,
         error_message,
     )
     .await
@@ -127,6 +130,7 @@ pub async fn get_run_by_session(session_id: &str) -> Result<Option<TaskRun>> {
                 http_request_count: m.http_request_count,
                 http_bytes_sent: m.http_bytes_sent,
                 http_bytes_received: m.http_bytes_received,
+                http_retry_count: m.http_retry_count,
                 task_code: None,
                 task_description: None,
                 task_comment: None,
@@ -0,0 +1,104 @@
+//! Cron-расписание регламентных заданий (`sys_tasks.schedule_cron`).
+//!
+//! Крейт `cron` понимает 6/7 полей (с секундами и годом). Стандартное 5-полевое
+//! выражение («*/5 * * * *») при сохранении дополняется секундами `0`, иначе
+//! воркер не мог бы его разобрать и запускал бы задачу по запасному интервалу.
+
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+/// Сколько ближайших запусков показывает предпросмотр расписания.
+pub const PREVIEW_RUNS: usize = 5;
+
+/// Приводит выражение к формату крейта `cron`: пробелы схлопываются, у
+/// 5-полевого выражения добавляется поле секунд.
+pub fn normalize(schedule_cron: &str) -> String {
+    let fields: Vec<&str> = schedule_cron.split_whitespace().collect();
+    if fields.len() == 5 {
+        format!("0 {}", fields.join(" "))
+    } else {
+        fields.join(" ")
+    }
+}
+
+fn parse(schedule_cron: &str) -> Option<cron::Schedule> {
+    cron::Schedule::from_str(&normalize(schedule_cron)).ok()
+}
+
+/// Проверка расписания при сохранении задачи. Пустое значение — задача без
+/// расписания (только ручной запуск); иначе возвращается нормализованное выражение.
+/// Текст ошибки — для пользователя.
+pub fn validate(schedule_cron: Option<&str>) -> Result<Option<String>, String> {
+    let Some(raw) = schedule_cron.map(str::trim).filter(|c| !c.is_empty()) else {
+        return Ok(None);
+    };
+    let normalized = normalize(raw);
+    let schedule = cron::Schedule::from_str(&normalized)
+        .map_err(|e| format!("Некорректное расписание «{}»: {}", raw, e))?;
+    if schedule.upcoming(Utc).next().is_none() {
+        return Err(format!(
+            "По расписанию «{}» не будет ни одного запуска",
+            raw
+        ));
+    }
+    Ok(Some(normalized))
+}
+
+/// Следующий запуск строго после `after`; `None` — выражение не разбирается
+/// или запусков больше не будет.
+pub fn next_run_after(schedule_cron: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    parse(schedule_cron)?.after(&after).next()
+}
+
+/// Ближайшие `count` запусков после `after` (для предпросмотра в UI).
+pub fn upcoming_runs(
+    schedule_cron: &str,
+    after: DateTime<Utc>,
+    count: usize,
+) -> Vec<DateTime<Utc>> {
+    parse(schedule_cron)
+        .map(|schedule| schedule.after(&after).take(count).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn five_field_expression_gets_seconds() {
+        assert_eq!(normalize("*/5 * * * *"), "0 */5 * * * *");
+        assert_eq!(normalize("  0 0 2  * * * "), "0 0 2 * * *");
+    }
+
+    #[test]
+    fn validate_rejects_garbage_and_keeps_empty_as_manual() {
+        assert_eq!(validate(None), Ok(None));
+        assert_eq!(validate(Some("   ")), Ok(None));
+        assert_eq!(
+            validate(Some("30 2 * * *")),
+            Ok(Some("0 30 2 * * *".to_string()))
+        );
+        assert!(validate(Some("every hour")).is_err());
+        assert!(validate(Some("0 0 25 * * *")).is_err());
+    }
+
+    #[test]
+    fn next_run_follows_schedule() {
+        let after = Utc.with_ymd_and_hms(2026, 3, 10, 2, 15, 0).unwrap();
+        assert_eq!(
+            next_run_after("0 30 2 * * *", after),
+            Some(Utc.with_ymd_and_hms(2026, 3, 10, 2, 30, 0).unwrap())
+        );
+        assert_eq!(
+            upcoming_runs("*/20 * * * *", after, 3),
+            vec![
+                Utc.with_ymd_and_hms(2026, 3, 10, 2, 20, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 3, 10, 2, 40, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 3, 10, 3, 0, 0).unwrap(),
+            ]
+        );
+        assert_eq!(next_run_after("not a cron", after), None);
+    }
+}
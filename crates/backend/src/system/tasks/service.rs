@@ -1,15 +1,10 @@
 use crate::system::tasks::change_token;
 use crate::system::tasks::repository;
+use crate::system::tasks::schedule;
 use anyhow::Result;
 use chrono::{DateTime, NaiveDate, Utc};
 use contracts::system::tasks::aggregate::{ScheduledTask, ScheduledTaskId};
 use contracts::system::tasks::request::{CreateScheduledTaskDto, UpdateScheduledTaskDto};
-use std::str::FromStr;
-
-fn next_run_from_cron(schedule_cron: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
-    let schedule = cron::Schedule::from_str(schedule_cron).ok()?;
-    schedule.after(&after).next()
-}
 
 fn next_run_for_enabled_schedule(
     schedule_cron: &Option<String>,
@@ -17,7 +12,7 @@ fn next_run_for_enabled_schedule(
 ) -> Option<DateTime<Utc>> {
     schedule_cron
         .as_deref()
-        .and_then(|cron| next_run_from_cron(cron, now))
+        .and_then(|cron| schedule::next_run_after(cron, now))
 }
 
 pub async fn list_all() -> Result<Vec<ScheduledTask>> {
@@ -1,10 +1,9 @@
 //! Единственное место, где живёт полный жизненный цикл одного запуска задачи:
-//! `timeout` → `QUOTA_EXHAUSTED` → watermark → `RunMetrics` → `finish_run`.
+//! `timeout` → `QUOTA_EXHAUSTED` → watermark → `RunMetrics` → `finish_run` → сводка подписчикам.
 //!
 //! Используется как воркером (плановый запуск), так и HTTP-хендлером (ручной запуск).
 //! Любое изменение политики завершения правится ровно здесь.
 
-use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -15,7 +14,7 @@ use contracts::system::tasks::progress::TaskStatus;
 
 use super::{
     abort_registry, change_token, logger::TaskLogger, registry::TaskManagerRegistry,
-    resource_coordinator::TaskResourceGuard, runs_service, service,
+    resource_coordinator::TaskResourceGuard, run_summary, runs_service, schedule, service,
 };
 
 fn progress_to_run_metrics(
@@ -29,14 +28,10 @@ fn progress_to_run_metrics(
         http_request_count: progress.http_request_count.map(|x| x as i64),
         http_bytes_sent: progress.http_bytes_sent,
         http_bytes_received: progress.http_bytes_received,
+        http_retry_count: progress.http_retry_count.map(|x| x as i64),
     }
 }
 
-fn next_run_from_cron(schedule_cron: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
-    let schedule = cron::Schedule::from_str(schedule_cron).ok()?;
-    schedule.after(&after).next()
-}
-
 fn effective_next_run_at(
     task: &ScheduledTask,
     proposed_next_run_at: DateTime<Utc>,
@@ -50,7 +45,7 @@ fn effective_next_run_at(
     let actual_next_run_at = task
         .schedule_cron
         .as_deref()
-        .and_then(|cron| next_run_from_cron(cron, finished_at))
+        .and_then(|cron| schedule::next_run_after(cron, finished_at))
         .unwrap_or_else(|| finished_at + chrono::Duration::hours(1));
 
     tracing::warn!(
@@ -82,6 +77,8 @@ pub struct TaskSessionParams {
     pub recompute_next_run_if_elapsed: bool,
     pub logger: Arc<TaskLogger>,
     pub registry: Arc<TaskManagerRegistry>,
+    /// Разослать подписчикам письмо-сводку после запуска (только плановые запуски).
+    pub send_run_summary: bool,
     /// Holds the task id and all declared write tables until the run future exits.
     pub resource_guard: TaskResourceGuard,
 }
@@ -99,6 +96,7 @@ pub fn spawn_task_session(params: TaskSessionParams) {
         recompute_next_run_if_elapsed,
         logger,
         registry,
+        send_run_summary,
         resource_guard,
     } = params;
 
@@ -156,8 +154,9 @@ pub fn spawn_task_session(params: TaskSessionParams) {
         let run_fut = manager.run(&task, &session_id_clone, logger);
         let timed = tokio::time::timeout(Duration::from_secs(timeout_secs), run_fut).await;
 
-        match timed {
+        let (final_status, final_error) = match timed {
             Ok(Ok(outcome)) => {
+                let final_status = outcome.status.clone();
                 let lsra_opt = if outcome.advances_watermark() {
                     Some(started_at)
                 } else {
@@ -201,6 +200,7 @@ pub fn spawn_task_session(params: TaskSessionParams) {
                 let _ = runs_service::finish_run(&session_id_clone, outcome.status, metrics, None)
                     .await;
                 change_token::TOKEN.bump();
+                (final_status, None)
             }
 
             Ok(Err(e)) => {
@@ -245,10 +245,11 @@ pub fn spawn_task_session(params: TaskSessionParams) {
                     manager
                         .get_progress(&session_id_clone)
                         .map(progress_to_run_metrics),
-                    Some(error_str),
+                    Some(error_str.clone()),
                 )
                 .await;
                 change_token::TOKEN.bump();
+                (TaskStatus::Failed, Some(error_str))
             }
 
             Err(_timeout) => {
@@ -276,22 +277,41 @@ pub fn spawn_task_session(params: TaskSessionParams) {
                     None,
                 )
                 .await;
+                let timeout_error = format!("Task exceeded max duration ({timeout_secs} seconds)");
                 let _ = runs_service::finish_run(
                     &session_id_clone,
                     TaskStatus::Failed,
                     manager
                         .get_progress(&session_id_clone)
                         .map(progress_to_run_metrics),
-                    Some(format!(
-                        "Task exceeded max duration ({timeout_secs} seconds)"
-                    )),
+                    Some(timeout_error.clone()),
                 )
                 .await;
                 change_token::TOKEN.bump();
+                (TaskStatus::Failed, Some(timeout_error))
             }
-        }
+        };
 
         abort_registry::remove(&session_id_clone);
+
+        if send_run_summary && run_summary::is_import_task(manager.metadata()) {
+            run_summary::notify_import_failed(
+                &task,
+                &session_id_clone,
+                final_status.clone(),
+                manager.get_progress(&session_id_clone),
+                final_error.clone(),
+            )
+            .await;
+            run_summary::notify_subscribers(
+                &task,
+                &session_id_clone,
+                final_status,
+                manager.get_progress(&session_id_clone),
+                final_error,
+            )
+            .await;
+        }
     });
 
     abort_registry::register(&session_id, join_handle.abort_handle());
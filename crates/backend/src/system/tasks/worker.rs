@@ -1,7 +1,6 @@
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::Utc;
 use contracts::domain::common::AggregateId;
-use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{info, warn};
 use uuid::Uuid;
@@ -9,16 +8,11 @@ use uuid::Uuid;
 use super::task_session_runner::{spawn_task_session, TaskSessionParams};
 use super::{
     logger::TaskLogger, registry::TaskManagerRegistry,
-    resource_coordinator::get_global_resource_coordinator, runs_service, service,
+    resource_coordinator::get_global_resource_coordinator, runs_service, schedule, service,
 };
+use contracts::system::tasks::metadata::TaskConcurrencyClass;
 use contracts::system::tasks::progress::TaskStatus;
 
-/// Следующее время запуска по cron-расписанию.
-fn next_run_from_cron(schedule_cron: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
-    let schedule = cron::Schedule::from_str(schedule_cron).ok()?;
-    schedule.after(&after).next()
-}
-
 /// Фоновый воркер: каждые `interval_seconds` проверяет все включённые задачи
 /// и запускает просроченные.
 pub struct ScheduledTaskWorker {
@@ -48,9 +42,11 @@ impl ScheduledTaskWorker {
         let mut interval =
             tokio::time::interval(tokio::time::Duration::from_secs(self.interval_seconds));
         interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        crate::system::health::worker_started(self.interval_seconds);
 
         loop {
             interval.tick().await;
+            crate::system::health::worker_heartbeat();
             info!("Checking for due scheduled tasks…");
             if let Err(e) = self.process_due_tasks().await {
                 tracing::error!("Error processing scheduled tasks: {:?}", e);
@@ -96,28 +92,39 @@ impl ScheduledTaskWorker {
             let session_id = Uuid::new_v4().to_string();
             let task_id = task.base.id;
             let task_label = format!("{} ({})", task.base.description, task_id_str);
-            let write_tables = self
+            // Неизвестный тип завершится ошибкой сразу в spawn_task_session — слот
+            // класса ему нужен только на это мгновение.
+            let (write_tables, concurrency_class) = self
                 .registry
                 .get(&task.task_type)
-                .map(|manager| manager.metadata().write_tables)
-                .unwrap_or_default();
-            let resource_guard =
-                match coordinator.try_acquire(&task_id_str, &task_label, &session_id, write_tables)
-                {
-                    Ok(guard) => guard,
-                    Err(conflict) => {
-                        warn!(
+                .map(|manager| {
+                    let metadata = manager.metadata();
+                    (metadata.write_tables, metadata.concurrency_class)
+                })
+                .unwrap_or((&[], TaskConcurrencyClass::Polling));
+            // Задачи идут в порядке next_run_at: при занятом слоте класса или таблице
+            // задача ждёт следующего тика, а более поздние задачи других классов стартуют.
+            let resource_guard = match coordinator.try_acquire(
+                &task_id_str,
+                &task_label,
+                &session_id,
+                write_tables,
+                concurrency_class,
+            ) {
+                Ok(guard) => guard,
+                Err(conflict) => {
+                    warn!(
                         "Task '{}' is due but resource '{}' is used by '{}'; retrying next tick",
                         task_label, conflict.resource, conflict.owner_task
                     );
-                        continue;
-                    }
-                };
+                    continue;
+                }
+            };
 
             let next_run = task
                 .schedule_cron
                 .as_deref()
-                .and_then(|cron| next_run_from_cron(cron, now))
+                .and_then(|cron| schedule::next_run_after(cron, now))
                 .unwrap_or_else(|| now + chrono::Duration::hours(1));
 
             let log_file_path = self.logger.get_log_file_path(&session_id);
@@ -152,6 +159,7 @@ impl ScheduledTaskWorker {
                 recompute_next_run_if_elapsed: true,
                 logger: Arc::clone(&self.logger),
                 registry: Arc::clone(&self.registry),
+                send_run_summary: true,
                 resource_guard,
             });
         }
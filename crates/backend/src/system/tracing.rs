@@ -1,5 +1,14 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+static LOG_FILE_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Путь к файлу журнала (`backend.log`) — для просмотра из админки.
+pub fn log_file_path() -> Option<&'static Path> {
+    LOG_FILE_PATH.get().map(PathBuf::as_path)
+}
+
 /// Инициализация системы трассировки (tracing)
 ///
 /// Логи пишутся в:
@@ -84,6 +93,7 @@ pub fn initialize() -> anyhow::Result<()> {
         )
         .init();
 
+    let _ = LOG_FILE_PATH.set(log_file_path);
     println!("✓ Tracing subscriber initialized");
     println!("========================================\n");
 
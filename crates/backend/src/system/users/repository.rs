@@ -10,8 +10,8 @@ pub async fn create_with_password(user: &User, password_hash: &str) -> Result<()
 
     conn.execute(Statement::from_sql_and_values(
         DatabaseBackend::Sqlite,
-        "INSERT INTO sys_users (id, username, email, password_hash, full_name, is_active, is_admin, primary_role_code, created_at, updated_at, last_login_at, created_by) 
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        "INSERT INTO sys_users (id, username, email, password_hash, full_name, is_active, is_admin, primary_role_code, created_at, updated_at, last_login_at, created_by, active_from, active_until) 
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         [
             user.id.clone().into(),
             user.username.clone().into(),
@@ -25,6 +25,8 @@ pub async fn create_with_password(user: &User, password_hash: &str) -> Result<()
             user.updated_at.clone().into(),
             user.last_login_at.clone().into(),
             user.created_by.clone().into(),
+            user.active_from.clone().into(),
+            user.active_until.clone().into(),
         ],
     ))
     .await
@@ -48,6 +50,8 @@ fn row_to_user(row: &sea_orm::QueryResult) -> Result<User> {
         updated_at: row.try_get("", "updated_at")?,
         last_login_at: row.try_get("", "last_login_at")?,
         created_by: row.try_get("", "created_by")?,
+        active_from: row.try_get("", "active_from")?,
+        active_until: row.try_get("", "active_until")?,
     })
 }
 
@@ -60,7 +64,7 @@ pub async fn get_by_id(id: &str) -> Result<Option<User>> {
     let result = conn
         .query_one(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
-            "SELECT id, username, email, full_name, is_active, is_admin, primary_role_code, created_at, updated_at, last_login_at, created_by 
+            "SELECT id, username, email, full_name, is_active, is_admin, primary_role_code, created_at, updated_at, last_login_at, created_by, active_from, active_until 
              FROM sys_users WHERE id = ?",
             [id.into()],
         ))
@@ -81,7 +85,7 @@ pub async fn get_by_username(username: &str) -> Result<Option<User>> {
     let result = conn
         .query_one(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
-            "SELECT id, username, email, full_name, is_active, is_admin, primary_role_code, created_at, updated_at, last_login_at, created_by 
+            "SELECT id, username, email, full_name, is_active, is_admin, primary_role_code, created_at, updated_at, last_login_at, created_by, active_from, active_until 
              FROM sys_users WHERE username = ?",
             [username.into()],
         ))
@@ -125,7 +129,7 @@ pub async fn list_all() -> Result<Vec<User>> {
     let rows = conn
         .query_all(Statement::from_string(
             DatabaseBackend::Sqlite,
-            "SELECT id, username, email, full_name, is_active, is_admin, primary_role_code, created_at, updated_at, last_login_at, created_by 
+            "SELECT id, username, email, full_name, is_active, is_admin, primary_role_code, created_at, updated_at, last_login_at, created_by, active_from, active_until 
              FROM sys_users ORDER BY created_at DESC".to_string(),
         ))
         .await?;
@@ -181,7 +185,7 @@ pub async fn find_active_by_email(email: &str) -> Result<Option<User>> {
     let result = conn
         .query_one(Statement::from_sql_and_values(
             DatabaseBackend::Sqlite,
-            "SELECT id, username, email, full_name, is_active, is_admin, primary_role_code, created_at, updated_at, last_login_at, created_by \
+            "SELECT id, username, email, full_name, is_active, is_admin, primary_role_code, created_at, updated_at, last_login_at, created_by, active_from, active_until \
              FROM sys_users \
              WHERE is_active = 1 AND LOWER(TRIM(email)) = ? LIMIT 1",
             [norm.into()],
@@ -203,7 +207,7 @@ pub async fn update(user: &User) -> Result<()> {
     conn.execute(Statement::from_sql_and_values(
         DatabaseBackend::Sqlite,
         "UPDATE sys_users 
-         SET email = ?, full_name = ?, is_active = ?, is_admin = ?, primary_role_code = ?, updated_at = ? 
+         SET email = ?, full_name = ?, is_active = ?, is_admin = ?, primary_role_code = ?, updated_at = ?, active_from = ?, active_until = ? 
          WHERE id = ?",
         [
             user.email.clone().into(),
@@ -212,6 +216,8 @@ pub async fn update(user: &User) -> Result<()> {
             (if user.is_admin { 1 } else { 0 }).into(),
             user.primary_role_code.clone().into(),
             user.updated_at.clone().into(),
+            user.active_from.clone().into(),
+            user.active_until.clone().into(),
             user.id.clone().into(),
         ],
     ))
@@ -299,3 +305,54 @@ pub async fn update_password(id: &str, password_hash: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Отключить учётную запись по истечении срока доступа и отозвать её refresh-токены,
+/// чтобы открытые сессии не продлевались.
+pub async fn deactivate_expired(id: &str) -> Result<()> {
+    use crate::shared::data::db::get_connection;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let conn = get_connection();
+
+    conn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "UPDATE sys_users SET is_active = 0, updated_at = ? WHERE id = ?",
+        [now.clone().into(), id.to_string().into()],
+    ))
+    .await
+    .context("Failed to deactivate user")?;
+
+    conn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "UPDATE sys_refresh_tokens SET revoked_at = ? WHERE user_id = ? AND revoked_at IS NULL",
+        [now.into(), id.to_string().into()],
+    ))
+    .await
+    .context("Failed to revoke refresh tokens")?;
+
+    Ok(())
+}
+
+/// Отметить, что напоминание об окончании срока `active_until` отправлено.
+/// `false` — по этому сроку уже напоминали (после продления срока напоминание придёт снова).
+pub async fn mark_expiry_reminded(id: &str, active_until: &str) -> Result<bool> {
+    use crate::shared::data::db::get_connection;
+
+    let conn = get_connection();
+
+    let result = conn
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE sys_users SET expiry_reminded_until = ? \
+             WHERE id = ? AND COALESCE(expiry_reminded_until, '') <> ?",
+            [
+                active_until.to_string().into(),
+                id.to_string().into(),
+                active_until.to_string().into(),
+            ],
+        ))
+        .await
+        .context("Failed to mark expiry reminder")?;
+
+    Ok(result.rows_affected() > 0)
+}
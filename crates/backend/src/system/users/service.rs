@@ -1,10 +1,20 @@
 use anyhow::Result;
-use chrono::Utc;
-use contracts::system::users::{ChangePasswordDto, CreateUserDto, UpdateUserDto, User};
+use chrono::{NaiveDate, Utc};
+use contracts::system::users::{
+    access_window, days_until_expiry, validate_access_period, AccessWindow, ChangePasswordDto,
+    CreateUserDto, ExpiringUserDto, UpdateUserDto, User, EXPIRY_REMINDER_DAYS,
+};
 
 use super::repository;
 use crate::system::auth::password;
 
+/// Пустая дата из формы — отсутствие ограничения.
+fn normalize_date(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
 /// Create a new user
 pub async fn create(dto: CreateUserDto, created_by: Option<String>) -> Result<String> {
     // Validate username
@@ -27,6 +37,11 @@ pub async fn create(dto: CreateUserDto, created_by: Option<String>) -> Result<St
         }
     }
 
+    let active_from = normalize_date(dto.active_from);
+    let active_until = normalize_date(dto.active_until);
+    validate_access_period(active_from.as_deref(), active_until.as_deref())
+        .map_err(|e| anyhow::anyhow!(e))?;
+
     // Validate password strength
     password::validate_password_strength(&dto.password)?;
 
@@ -55,6 +70,8 @@ pub async fn create(dto: CreateUserDto, created_by: Option<String>) -> Result<St
         updated_at: now,
         last_login_at: None,
         created_by,
+        active_from,
+        active_until,
     };
 
     repository::create_with_password(&user, &password_hash).await?;
@@ -76,6 +93,21 @@ pub async fn update(dto: UpdateUserDto) -> Result<()> {
         }
     }
 
+    let active_from = normalize_date(dto.active_from);
+    let active_until = normalize_date(dto.active_until);
+    validate_access_period(active_from.as_deref(), active_until.as_deref())
+        .map_err(|e| anyhow::anyhow!(e))?;
+    // Иначе регламентное задание сразу отключит запись снова
+    let today = Utc::now().date_naive();
+    if dto.is_active
+        && access_window(active_from.as_deref(), active_until.as_deref(), today)
+            == AccessWindow::Expired
+    {
+        return Err(anyhow::anyhow!(
+            "Access period has expired: extend active_until to activate the user"
+        ));
+    }
+
     // Update fields
     _user.email = dto.email;
     _user.full_name = dto.full_name;
@@ -86,6 +118,8 @@ pub async fn update(dto: UpdateUserDto) -> Result<()> {
     } else {
         dto.primary_role_code
     };
+    _user.active_from = active_from;
+    _user.active_until = active_until;
     _user.updated_at = Utc::now().to_rfc3339();
 
     repository::update(&_user).await?;
@@ -162,7 +196,7 @@ pub async fn verify_credentials(username: &str, password: &str) -> Result<Option
     };
 
     // Check if user is active
-    if !user.is_active {
+    if !is_access_allowed(&user, Utc::now().date_naive()) {
         return Err(anyhow::anyhow!("User account is inactive"));
     }
 
@@ -181,3 +215,81 @@ pub async fn verify_credentials(username: &str, password: &str) -> Result<Option
 
     Ok(Some(user))
 }
+
+/// Учётная запись активна и сегодняшний день входит в срок её действия.
+pub fn is_access_allowed(user: &User, today: NaiveDate) -> bool {
+    user.is_active
+        && access_window(
+            user.active_from.as_deref(),
+            user.active_until.as_deref(),
+            today,
+        ) == AccessWindow::Active
+}
+
+/// Отчёт «Истекающие учётные записи»: срок заканчивается в ближайшие `days` дней
+/// или уже истёк. Сначала ближайшие.
+pub async fn list_expiring(days: i64, today: NaiveDate) -> Result<Vec<ExpiringUserDto>> {
+    let mut rows: Vec<ExpiringUserDto> = repository::list_all()
+        .await?
+        .into_iter()
+        .filter_map(|user| {
+            let days_left = days_until_expiry(user.active_until.as_deref(), today)?;
+            (days_left <= days).then(|| ExpiringUserDto {
+                id: user.id,
+                username: user.username,
+                full_name: user.full_name,
+                email: user.email,
+                is_active: user.is_active,
+                active_until: user.active_until.unwrap_or_default(),
+                days_left,
+                last_login_at: user.last_login_at,
+            })
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        a.days_left
+            .cmp(&b.days_left)
+            .then_with(|| a.username.cmp(&b.username))
+    });
+    Ok(rows)
+}
+
+/// Отключает активные учётные записи с истёкшим сроком; возвращает отключённые.
+pub async fn deactivate_expired(today: NaiveDate) -> Result<Vec<User>> {
+    let mut deactivated = Vec::new();
+    for user in repository::list_all().await? {
+        let expired = access_window(
+            user.active_from.as_deref(),
+            user.active_until.as_deref(),
+            today,
+        ) == AccessWindow::Expired;
+        if user.is_active && expired {
+            repository::deactivate_expired(&user.id).await?;
+            deactivated.push(user);
+        }
+    }
+    Ok(deactivated)
+}
+
+/// Активные учётные записи, срок которых истекает в ближайшие [`EXPIRY_REMINDER_DAYS`]
+/// дней и по которым ещё не напоминали; напоминание сразу отмечается отправленным.
+pub async fn take_due_expiry_reminders(today: NaiveDate) -> Result<Vec<(User, i64)>> {
+    let mut due = Vec::new();
+    for user in repository::list_all().await? {
+        if !user.is_active {
+            continue;
+        }
+        let (Some(until), Some(days_left)) = (
+            user.active_until.clone(),
+            days_until_expiry(user.active_until.as_deref(), today),
+        ) else {
+            continue;
+        };
+        if (0..=EXPIRY_REMINDER_DAYS).contains(&days_left)
+            && repository::mark_expiry_reminded(&user.id, &until).await?
+        {
+            due.push((user, days_left));
+        }
+    }
+    Ok(due)
+}
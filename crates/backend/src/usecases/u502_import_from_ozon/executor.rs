@@ -3,6 +3,8 @@ use super::{
     processors::{postings, product, realization, returns, sales, transaction},
     progress_tracker::ProgressTracker,
 };
+use crate::shared::marketplaces::parse::{report_summary, FieldErrors};
+use crate::shared::marketplaces::sandbox;
 use anyhow::Result;
 use contracts::domain::common::AggregateId;
 use contracts::system::tasks::progress::TaskProgress;
@@ -210,6 +212,21 @@ impl ImportExecutor {
         connection: &contracts::domain::a006_connection_mp::aggregate::ConnectionMP,
     ) -> Result<()> {
         for aggregate_index in &request.target_aggregates {
+            if sandbox::skips_import(connection, aggregate_index) {
+                tracing::info!(
+                    "Import of {} skipped: connection {} is in sandbox mode",
+                    aggregate_index,
+                    connection.base.id.as_string()
+                );
+                self.progress_tracker.set_current_item(
+                    session_id,
+                    aggregate_index,
+                    Some("Пропущено: подключение в режиме песочницы".to_string()),
+                );
+                self.progress_tracker
+                    .complete_aggregate(session_id, aggregate_index);
+                continue;
+            }
             match aggregate_index.as_str() {
                 "a007_marketplace_product" => {
                     self.import_marketplace_products(session_id, connection)
@@ -1115,11 +1132,19 @@ impl ImportExecutor {
         // Пагинация по страницам
         let page_size = 1000; // Максимум для OZON API
         let mut current_page = 1;
+        let mut field_errors = FieldErrors::default();
 
         loop {
             let resp = self
                 .api_client
-                .fetch_transactions_list(connection, date_from, date_to, current_page, page_size)
+                .fetch_transactions_list(
+                    connection,
+                    date_from,
+                    date_to,
+                    current_page,
+                    page_size,
+                    |_| self.progress_tracker.record_http_retry(session_id),
+                )
                 .await?;
 
             let operations_count = resp.result.operations.len();
@@ -1135,8 +1160,13 @@ impl ImportExecutor {
             );
 
             for operation in resp.result.operations {
-                match transaction::process_transaction(connection, &organization_id, &operation)
-                    .await
+                match transaction::process_transaction(
+                    connection,
+                    &organization_id,
+                    &operation,
+                    &mut field_errors,
+                )
+                .await
                 {
                     Ok(is_new) => {
                         if is_new {
@@ -1177,6 +1207,16 @@ impl ImportExecutor {
             current_page += 1;
         }
 
+        if let Some((message, details)) = report_summary(&field_errors.into_vec()) {
+            tracing::warn!("OZON Transactions import: {}", message);
+            self.progress_tracker.add_error(
+                session_id,
+                Some(aggregate_index.to_string()),
+                message,
+                Some(details),
+            );
+        }
+
         tracing::info!(
             "OZON Transactions import completed: processed={}, inserted={}, updated={}",
             total_processed,
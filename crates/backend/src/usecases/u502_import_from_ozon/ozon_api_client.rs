@@ -1,3 +1,6 @@
+use crate::shared::marketplaces::retry::{send_with_retry, RetryEvent, RetryPolicy};
+use crate::shared::marketplaces::sandbox;
+use crate::shared::marketplaces::{field_mapping, schema_guard};
 use anyhow::Result;
 use chrono::Datelike;
 use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
@@ -6,6 +9,9 @@ use serde::{Deserialize, Serialize};
 use std::fs::OpenOptions;
 use std::io::Write;
 
+/// Эндпоинт в журнале расхождений схемы (`sys_schema_drift`).
+const OZON_TRANSACTIONS_ENDPOINT: &str = "Ozon /v3/finance/transaction/list";
+
 /// HTTP-клиент для работы с OZON Seller API
 pub struct OzonApiClient {
     client: reqwest::Client,
@@ -47,7 +53,7 @@ impl OzonApiClient {
         limit: i32,
         last_id: Option<String>,
     ) -> Result<OzonProductListResponse> {
-        let url = "https://api-seller.ozon.ru/v3/product/list";
+        let url = &sandbox::endpoint(connection, "https://api-seller.ozon.ru/v3/product/list")?;
 
         // Проверка обязательных полей для OZON API
         let client_id = connection
@@ -148,7 +154,10 @@ impl OzonApiClient {
         connection: &ConnectionMP,
         product_ids: Vec<i64>,
     ) -> Result<OzonProductInfoResponse> {
-        let url = "https://api-seller.ozon.ru/v3/product/info/list";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://api-seller.ozon.ru/v3/product/info/list",
+        )?;
 
         // Проверка обязательных полей для OZON API
         let client_id = connection
@@ -235,7 +244,10 @@ impl OzonApiClient {
         page: i32,
         page_size: i32,
     ) -> Result<OzonFinanceTransactionResponse> {
-        let url = "https://api-seller.ozon.ru/v3/finance/transaction/list";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://api-seller.ozon.ru/v3/finance/transaction/list",
+        )?;
 
         let client_id = connection
             .application_id
@@ -328,7 +340,7 @@ impl OzonApiClient {
         last_id: i64,
         limit: i32,
     ) -> Result<OzonReturnsListResponse> {
-        let url = "https://api-seller.ozon.ru/v1/returns/list";
+        let url = &sandbox::endpoint(connection, "https://api-seller.ozon.ru/v1/returns/list")?;
 
         let client_id = connection
             .application_id
@@ -418,16 +430,6 @@ impl OzonApiClient {
         limit: i32,
         offset: i32,
     ) -> Result<OzonPostingListResponse> {
-        let url = "https://api-seller.ozon.ru/v3/posting/fbs/list";
-
-        let client_id = connection
-            .application_id
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Client-Id required for OZON API"))?;
-        if connection.api_key.trim().is_empty() {
-            anyhow::bail!("Api-Key required for OZON API");
-        }
-
         let time_from = format!("{}T00:00:00Z", date_from.format("%Y-%m-%d"));
         let time_to = format!("{}T23:59:59Z", date_to.format("%Y-%m-%d"));
 
@@ -436,12 +438,61 @@ impl OzonApiClient {
                 since: Some(time_from),
                 to: Some(time_to),
                 status: None, // Import all statuses (delivered, cancelled, etc.)
+                last_changed_status_date: None,
             },
             limit: Some(limit),
             offset: Some(offset),
         };
+        self.post_fbs_postings_list(connection, &request_body).await
+    }
 
-        let body = serde_json::to_string(&request_body)?;
+    /// FBS отправления, статус которых менялся в `[changed_from, changed_to]`
+    /// (фильтр `last_changed_status_date`). `since` — нижняя граница даты создания:
+    /// без неё API запрос не принимает.
+    pub async fn fetch_fbs_postings_status_changed(
+        &self,
+        connection: &ConnectionMP,
+        since: chrono::DateTime<chrono::Utc>,
+        changed_from: chrono::DateTime<chrono::Utc>,
+        changed_to: chrono::DateTime<chrono::Utc>,
+        limit: i32,
+        offset: i32,
+    ) -> Result<OzonPostingListResponse> {
+        let format = |dt: chrono::DateTime<chrono::Utc>| {
+            dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        };
+        let request_body = OzonPostingListRequest {
+            filter: OzonPostingFilter {
+                since: Some(format(since)),
+                to: Some(format(changed_to)),
+                status: None,
+                last_changed_status_date: Some(OzonDateRange {
+                    from: format(changed_from),
+                    to: format(changed_to),
+                }),
+            },
+            limit: Some(limit),
+            offset: Some(offset),
+        };
+        self.post_fbs_postings_list(connection, &request_body).await
+    }
+
+    async fn post_fbs_postings_list(
+        &self,
+        connection: &ConnectionMP,
+        request_body: &OzonPostingListRequest,
+    ) -> Result<OzonPostingListResponse> {
+        let url = &sandbox::endpoint(connection, "https://api-seller.ozon.ru/v3/posting/fbs/list")?;
+
+        let client_id = connection
+            .application_id
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Client-Id required for OZON API"))?;
+        if connection.api_key.trim().is_empty() {
+            anyhow::bail!("Api-Key required for OZON API");
+        }
+
+        let body = serde_json::to_string(request_body)?;
         self.log_to_file(&format!(
             "=== FBS POSTINGS REQUEST ===\nPOST {}\nBody: {}",
             url, body
@@ -503,7 +554,7 @@ impl OzonApiClient {
         limit: i32,
         offset: i32,
     ) -> Result<OzonFboPostingListResponse> {
-        let url = "https://api-seller.ozon.ru/v2/posting/fbo/list";
+        let url = &sandbox::endpoint(connection, "https://api-seller.ozon.ru/v2/posting/fbo/list")?;
 
         let client_id = connection
             .application_id
@@ -521,6 +572,7 @@ impl OzonApiClient {
                 since: Some(time_from),
                 to: Some(time_to),
                 status: None, // Import all statuses (delivered, cancelled, etc.)
+                last_changed_status_date: None,
             },
             limit: Some(limit),
             offset: Some(offset),
@@ -861,6 +913,15 @@ pub struct OzonPostingFilter {
     pub to: Option<String>, // ISO datetime
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<String>,
+    /// Период последней смены статуса (только v3/posting/fbs/list)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_changed_status_date: Option<OzonDateRange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OzonDateRange {
+    pub from: String, // ISO datetime
+    pub to: String,   // ISO datetime
 }
 
 // FBS Postings (v3/posting/fbs/list) - returns array
@@ -949,7 +1010,10 @@ impl OzonApiClient {
         limit: i32,
         offset: i32,
     ) -> Result<OzonFinanceRealizationResponse> {
-        let url = "https://api-seller.ozon.ru/v1/finance/realization/posting";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://api-seller.ozon.ru/v1/finance/realization/posting",
+        )?;
 
         let client_id = connection
             .application_id
@@ -1200,8 +1264,12 @@ impl OzonApiClient {
         date_to: chrono::NaiveDate,
         page: i32,
         page_size: i32,
+        mut on_retry: impl FnMut(&RetryEvent),
     ) -> Result<OzonTransactionsListResponse> {
-        let url = "https://api-seller.ozon.ru/v3/finance/transaction/list";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://api-seller.ozon.ru/v3/finance/transaction/list",
+        )?;
 
         let client_id = connection
             .application_id
@@ -1231,15 +1299,29 @@ impl OzonApiClient {
             url, body
         ));
 
-        let response = match self
-            .client
-            .post(url)
-            .header("Client-Id", client_id)
-            .header("Api-Key", &connection.api_key)
-            .header("Content-Type", "application/json")
-            .body(body)
-            .send()
-            .await
+        let response = match send_with_retry(
+            &RetryPolicy::from_config(),
+            "OZON Transactions API",
+            || {
+                self.client
+                    .post(url)
+                    .header("Client-Id", client_id)
+                    .header("Api-Key", &connection.api_key)
+                    .header("Content-Type", "application/json")
+                    .body(body.clone())
+            },
+            |event| {
+                self.log_to_file(&format!(
+                    "{}: retry {}/{} in {:.1}s",
+                    event.reason,
+                    event.retry,
+                    event.max_attempts - 1,
+                    event.delay.as_secs_f64()
+                ));
+                on_retry(event);
+            },
+        )
+        .await
         {
             Ok(resp) => resp,
             Err(e) => {
@@ -1277,10 +1359,28 @@ impl OzonApiClient {
         let body = response.text().await?;
         self.log_to_file(&format!("=== RESPONSE BODY ===\n{}\n", body));
 
-        match serde_json::from_str::<OzonTransactionsListResponse>(&body) {
-            Ok(data) => {
+        match serde_json::from_str::<OzonTransactionsListEnvelope>(&body) {
+            Ok(envelope) => {
                 self.log_to_file("Successfully parsed transactions JSON");
-                Ok(data)
+                let guarded = schema_guard::guard_rows::<OzonTransactionOperation>(
+                    OZON_TRANSACTIONS_ENDPOINT,
+                    &field_mapping::a014_ozon_transactions::MAPPING,
+                    envelope.result.operations,
+                )
+                .await;
+                if guarded.rejected > 0 {
+                    self.log_to_file(&format!(
+                        "Schema drift: {} operations rejected (see quality check schema_drift)",
+                        guarded.rejected
+                    ));
+                }
+                Ok(OzonTransactionsListResponse {
+                    result: OzonTransactionsListResult {
+                        operations: guarded.rows.into_iter().map(|(op, _)| op).collect(),
+                        page_count: envelope.result.page_count,
+                        row_count: envelope.result.row_count,
+                    },
+                })
             }
             Err(e) => {
                 let preview: String = body.chars().take(500).collect();
@@ -1294,6 +1394,130 @@ impl OzonApiClient {
     }
 }
 
+impl OzonApiClient {
+    /// Итоги по транзакциям за период через POST /v3/finance/transaction/totals
+    pub async fn fetch_transaction_totals(
+        &self,
+        connection: &ConnectionMP,
+        date_from: chrono::NaiveDate,
+        date_to: chrono::NaiveDate,
+    ) -> Result<OzonTransactionTotals> {
+        let url = &sandbox::endpoint(
+            connection,
+            "https://api-seller.ozon.ru/v3/finance/transaction/totals",
+        )?;
+
+        let client_id = connection
+            .application_id
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Client-Id required for OZON API"))?;
+        if connection.api_key.trim().is_empty() {
+            anyhow::bail!("Api-Key required for OZON API");
+        }
+
+        let request_body = OzonTransactionTotalsRequest {
+            date: OzonTransactionsDateFilter {
+                from: format!("{}T00:00:00.000Z", date_from),
+                to: format!("{}T23:59:59.999Z", date_to),
+            },
+            posting_number: String::new(),
+            transaction_type: "all".to_string(),
+        };
+
+        let body = serde_json::to_string(&request_body)?;
+        self.log_to_file(&format!(
+            "=== TRANSACTION TOTALS REQUEST ===\nPOST {}\nBody: {}",
+            url, body
+        ));
+
+        let response = self
+            .client
+            .post(url)
+            .header("Client-Id", client_id)
+            .header("Api-Key", &connection.api_key)
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| {
+                self.log_to_file(&format!("HTTP request failed: {:?}", e));
+                anyhow::anyhow!("OZON Transaction totals request failed: {}", e)
+            })?;
+
+        let status = response.status();
+        self.log_to_file(&format!("Response status: {}", status));
+        let body = response.text().await?;
+        self.log_to_file(&format!("=== RESPONSE BODY ===\n{}\n", body));
+
+        if !status.is_success() {
+            anyhow::bail!(
+                "OZON Transaction totals request failed with status {}: {}",
+                status,
+                body
+            );
+        }
+
+        serde_json::from_str::<OzonTransactionTotalsResponse>(&body)
+            .map(|data| data.result)
+            .map_err(|e| {
+                let preview: String = body.chars().take(500).collect();
+                anyhow::anyhow!(
+                    "Failed to parse transaction totals JSON: {}. Body: {}",
+                    e,
+                    preview
+                )
+            })
+    }
+}
+
+/// Запрос итогов по транзакциям
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OzonTransactionTotalsRequest {
+    pub date: OzonTransactionsDateFilter,
+    pub posting_number: String,
+    pub transaction_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OzonTransactionTotalsResponse {
+    pub result: OzonTransactionTotals,
+}
+
+/// Итоги по видам начислений; сумма всех полей — сальдо операций за период
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OzonTransactionTotals {
+    #[serde(default)]
+    pub accruals_for_sale: f64,
+    #[serde(default)]
+    pub sale_commission: f64,
+    #[serde(default)]
+    pub processing_and_delivery: f64,
+    #[serde(default)]
+    pub refunds_and_cancellations: f64,
+    #[serde(default)]
+    pub services_amount: f64,
+    #[serde(default)]
+    pub compensation_amount: f64,
+    #[serde(default)]
+    pub money_transfer: f64,
+    #[serde(default)]
+    pub others_amount: f64,
+}
+
+impl OzonTransactionTotals {
+    /// Сальдо: должно совпадать с суммой `amount` операций за тот же период
+    pub fn net_amount(&self) -> f64 {
+        self.accruals_for_sale
+            + self.sale_commission
+            + self.processing_and_delivery
+            + self.refunds_and_cancellations
+            + self.services_amount
+            + self.compensation_amount
+            + self.money_transfer
+            + self.others_amount
+    }
+}
+
 /// Запрос на получение списка транзакций
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OzonTransactionsListRequest {
@@ -1329,6 +1553,21 @@ pub struct OzonTransactionsListResult {
     pub row_count: i32,
 }
 
+/// Ответ списка транзакций до разбора операций: операции разбираются
+/// построчно через `schema_guard`, чтобы новая схема не роняла всю страницу.
+#[derive(Debug, Deserialize)]
+struct OzonTransactionsListEnvelope {
+    result: OzonTransactionsListEnvelopeResult,
+}
+
+#[derive(Debug, Deserialize)]
+struct OzonTransactionsListEnvelopeResult {
+    #[serde(default)]
+    operations: Vec<serde_json::Value>,
+    page_count: i32,
+    row_count: i32,
+}
+
 /// Одна операция (транзакция)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OzonTransactionOperation {
@@ -1348,6 +1587,10 @@ pub struct OzonTransactionOperation {
     pub items: Vec<OzonTransactionItem>,
     #[serde(default)]
     pub services: Vec<OzonTransactionService>,
+    /// Поля, не описанные в структуре: сохраняются в raw JSON и доступны
+    /// таблице `field_mapping::a014_ozon_transactions` без правки клиента
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,7 +77,7 @@ pub async fn process_fbs_posting(
     };
 
     let status_norm = normalize_ozon_status(&posting.status);
-    let is_posted = true;
+    let is_posted = !connection.is_sandbox;
 
     let state = OzonFbsPostingState {
         status_raw: posting.status.clone(),
@@ -93,7 +93,7 @@ pub async fn process_fbs_posting(
         document_version: 1,
     };
 
-    let document = OzonFbsPosting::new_for_insert(
+    let mut document = OzonFbsPosting::new_for_insert(
         posting_number.clone(),
         format!("FBS Posting {}", posting_number),
         header,
@@ -102,6 +102,7 @@ pub async fn process_fbs_posting(
         source_meta,
         is_posted,
     );
+    document.is_sandbox = connection.is_sandbox;
 
     let raw_json = serde_json::to_string(posting)?;
     a010_ozon_fbs_posting::service::store_document_with_raw(document, &raw_json).await?;
@@ -169,7 +170,7 @@ pub async fn process_fbo_posting(
     };
 
     let status_norm = normalize_ozon_status(&posting.status);
-    let is_posted = true;
+    let is_posted = !connection.is_sandbox;
 
     let state = OzonFboPostingState {
         status_raw: posting.status.clone(),
@@ -186,7 +187,7 @@ pub async fn process_fbo_posting(
         document_version: 1,
     };
 
-    let document = OzonFboPosting::new_for_insert(
+    let mut document = OzonFboPosting::new_for_insert(
         posting_number.clone(),
         format!("FBO Posting {}", posting_number),
         header,
@@ -195,6 +196,7 @@ pub async fn process_fbo_posting(
         source_meta,
         is_posted,
     );
+    document.is_sandbox = connection.is_sandbox;
 
     let raw_json = serde_json::to_string(posting)?;
     a011_ozon_fbo_posting::service::store_document_with_raw(document, &raw_json).await?;
@@ -1,5 +1,7 @@
 use super::super::ozon_api_client::OzonTransactionOperation;
 use crate::domain::a014_ozon_transactions;
+use crate::shared::marketplaces::field_mapping::a014_ozon_transactions::MAPPING;
+use crate::shared::marketplaces::parse::FieldErrors;
 use anyhow::Result;
 use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
 use contracts::domain::a014_ozon_transactions::aggregate::{
@@ -8,29 +10,39 @@ use contracts::domain::a014_ozon_transactions::aggregate::{
 };
 use contracts::domain::common::AggregateId;
 
+/// Поля операции читаются по таблице `field_mapping::a014_ozon_transactions`;
+/// ключ upsert (`operation_id`) берётся из структуры. Неразобранные значения
+/// полей добавляются в `field_errors` для отчёта импорта.
 pub async fn process_transaction(
     connection: &ConnectionMP,
     organization_id: &str,
     operation: &OzonTransactionOperation,
+    field_errors: &mut FieldErrors,
 ) -> Result<bool> {
-    let code = format!("OZON-TXN-{}", operation.operation_id);
-    let description = format!(
-        "{} - {}",
-        operation.operation_type_name, operation.posting.posting_number
+    let raw_value = serde_json::to_value(operation)?;
+    let payload = MAPPING.apply(&raw_value);
+    field_errors.extend(
+        &format!("операция {}", operation.operation_id),
+        payload.parse_errors(),
     );
+    let posting_number = payload.str("posting_number").unwrap_or_default();
+    let operation_type_name = payload.str("operation_type_name").unwrap_or_default();
+
+    let code = format!("OZON-TXN-{}", operation.operation_id);
+    let description = format!("{} - {}", operation_type_name, posting_number);
 
     // Собираем header
     let header = OzonTransactionsHeader {
         operation_id: operation.operation_id,
-        operation_type: operation.operation_type.clone(),
-        operation_date: operation.operation_date.clone(),
-        operation_type_name: operation.operation_type_name.clone(),
-        delivery_charge: operation.delivery_charge,
-        return_delivery_charge: operation.return_delivery_charge,
-        accruals_for_sale: operation.accruals_for_sale,
-        sale_commission: operation.sale_commission,
-        amount: operation.amount,
-        transaction_type: operation.transaction_type.clone(),
+        operation_type: payload.str("operation_type").unwrap_or_default(),
+        operation_date: payload.str("operation_date").unwrap_or_default(),
+        operation_type_name,
+        delivery_charge: payload.f64("delivery_charge").unwrap_or(0.0),
+        return_delivery_charge: payload.f64("return_delivery_charge").unwrap_or(0.0),
+        accruals_for_sale: payload.f64("accruals_for_sale").unwrap_or(0.0),
+        sale_commission: payload.f64("sale_commission").unwrap_or(0.0),
+        amount: payload.f64("amount").unwrap_or(0.0),
+        transaction_type: payload.str("transaction_type").unwrap_or_default(),
         connection_id: connection.base.id.as_string(),
         organization_id: organization_id.to_string(),
         marketplace_id: connection.marketplace_id.clone(),
@@ -38,19 +50,19 @@ pub async fn process_transaction(
 
     // Собираем posting
     let posting = OzonTransactionsPosting {
-        delivery_schema: operation.posting.delivery_schema.clone(),
-        order_date: operation.posting.order_date.clone(),
-        posting_number: operation.posting.posting_number.clone(),
-        warehouse_id: operation.posting.warehouse_id,
+        delivery_schema: payload.str("delivery_schema").unwrap_or_default(),
+        order_date: payload.str("order_date").unwrap_or_default(),
+        posting_number,
+        warehouse_id: payload.i64("warehouse_id").unwrap_or(0),
     };
 
     // Собираем items
-    let items: Vec<OzonTransactionsItem> = operation
-        .items
+    let items: Vec<OzonTransactionsItem> = payload
+        .list("items")
         .iter()
         .map(|item| OzonTransactionsItem {
-            name: item.name.clone(),
-            sku: item.sku,
+            name: item.str("name").unwrap_or_default(),
+            sku: item.i64("sku").unwrap_or(0),
             price: None,
             ratio: None,
             marketplace_product_ref: None,
@@ -59,12 +71,12 @@ pub async fn process_transaction(
         .collect();
 
     // Собираем services
-    let services: Vec<OzonTransactionsService> = operation
-        .services
+    let services: Vec<OzonTransactionsService> = payload
+        .list("services")
         .iter()
         .map(|service| OzonTransactionsService {
-            name: service.name.clone(),
-            price: service.price,
+            name: service.str("name").unwrap_or_default(),
+            price: service.f64("price").unwrap_or(0.0),
         })
         .collect();
 
@@ -72,11 +84,11 @@ pub async fn process_transaction(
     let source_meta = OzonTransactionsSourceMeta {
         raw_payload_ref: format!("ozon_txn_{}", operation.operation_id),
         fetched_at: chrono::Utc::now(),
-        document_version: 1,
+        document_version: payload.version() as i32,
     };
 
     // Создаем агрегат
-    let aggregate = OzonTransactions::new_for_insert(
+    let mut aggregate = OzonTransactions::new_for_insert(
         code,
         description,
         header,
@@ -87,6 +99,15 @@ pub async fn process_transaction(
         false, // is_posted = false по умолчанию
     );
 
+    // Привязка строк к a007 по SKU; ошибка привязки не прерывает импорт
+    if let Err(e) = a014_ozon_transactions::sku_linking::link_items(&mut aggregate, false).await {
+        tracing::warn!(
+            "Failed to link SKUs for Ozon operation {}: {}",
+            aggregate.header.operation_id,
+            e
+        );
+    }
+
     // Upsert по operation_id
     let existing =
         a014_ozon_transactions::repository::get_by_operation_id(aggregate.header.operation_id)
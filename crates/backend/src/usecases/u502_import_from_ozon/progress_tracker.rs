@@ -155,6 +155,15 @@ impl ProgressTracker {
         }
     }
 
+    /// Повтор запроса к OZON API после временного сбоя
+    pub fn record_http_retry(&self, session_id: &str) {
+        let mut sessions = self.sessions.write().unwrap();
+        if let Some(progress) = sessions.get_mut(session_id) {
+            progress.http_retry_count = progress.http_retry_count.saturating_add(1);
+            progress.updated_at = chrono::Utc::now();
+        }
+    }
+
     /// Завершить сессию импорта
     pub fn complete_session(&self, session_id: &str, status: ImportStatus) {
         let mut sessions = self.sessions.write().unwrap();
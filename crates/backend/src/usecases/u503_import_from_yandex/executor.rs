@@ -3,6 +3,8 @@ use super::{
     progress_tracker::ProgressTracker,
     yandex_api_client::{OrderDateField, YandexApiClient},
 };
+use crate::shared::marketplaces::parse::report_summary;
+use crate::shared::marketplaces::sandbox;
 use anyhow::Result;
 use contracts::domain::common::AggregateId;
 use contracts::system::tasks::progress::TaskProgress;
@@ -167,6 +169,21 @@ impl ImportExecutor {
         connection: &contracts::domain::a006_connection_mp::aggregate::ConnectionMP,
     ) -> Result<()> {
         for aggregate_index in &request.target_aggregates {
+            if sandbox::skips_import(connection, aggregate_index) {
+                tracing::info!(
+                    "Import of {} skipped: connection {} is in sandbox mode",
+                    aggregate_index,
+                    connection.base.id.as_string()
+                );
+                self.progress_tracker.set_current_item(
+                    session_id,
+                    aggregate_index,
+                    Some("Пропущено: подключение в режиме песочницы".to_string()),
+                );
+                self.progress_tracker
+                    .complete_aggregate(session_id, aggregate_index);
+                continue;
+            }
             match aggregate_index.as_str() {
                 "a007_marketplace_product" => {
                     self.import_marketplace_products(session_id, connection)
@@ -894,6 +911,15 @@ impl ImportExecutor {
                 parsed.skipped
             );
         }
+        if let Some((message, details)) = report_summary(&parsed.field_errors) {
+            tracing::warn!("YM payment report: {}", message);
+            self.progress_tracker.add_error(
+                session_id,
+                Some(aggregate_index.to_string()),
+                message,
+                Some(details),
+            );
+        }
 
         // Phase 5: bulk-upsert raw rows in batches. One multi-row statement per
         // batch replaces thousands of per-row autocommit transactions — the main
@@ -1177,6 +1203,15 @@ impl ImportExecutor {
                 );
                 e
             })?;
+            if let Some((message, details)) = report_summary(&parsed.field_errors) {
+                tracing::warn!("YM realization {}-{:02}: {}", year, month, message);
+                self.progress_tracker.add_error(
+                    session_id,
+                    Some(aggregate_index.to_string()),
+                    format!("{} ({}-{:02})", message, year, month),
+                    Some(details),
+                );
+            }
             let documents = parsed.documents;
 
             // Phase 5: replace_for_period (кабинет × месяц) — идемпотентно.
@@ -1200,9 +1235,12 @@ impl ImportExecutor {
                 e
             })?;
 
-            // Phase 6: провести каждый документ в GL (слой ybuh).
+            // Phase 6: провести каждый документ в GL (слой ybuh); документы песочницы не проводятся.
             for document in &documents {
                 doc_total += 1;
+                if connection.is_sandbox {
+                    continue;
+                }
                 let id = Uuid::parse_str(&document.base.id.as_string())
                     .map_err(|e| anyhow::anyhow!("Invalid document id: {}", e))?;
                 match crate::domain::a034_ym_realization::posting::post_document(id).await {
@@ -229,15 +229,16 @@ pub async fn process_order(
         document_version: 1,
     };
 
-    let document = YmOrder::new_for_insert(
+    let mut document = YmOrder::new_for_insert(
         order_id_str.clone(),
         format!("YM Order {}", order_id_str),
         header,
         lines,
         state,
         source_meta,
-        true,
+        !connection.is_sandbox,
     );
+    document.is_sandbox = connection.is_sandbox;
 
     let raw_json = serde_json::to_string(&order_details)?;
     a013_ym_order::service::store_document_with_raw(document, &raw_json).await?;
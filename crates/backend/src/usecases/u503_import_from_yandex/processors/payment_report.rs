@@ -3,6 +3,7 @@ use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
 use contracts::domain::common::AggregateId;
 
 use crate::projections::p907_ym_payment_report::repository::YmPaymentReportEntry;
+use crate::shared::marketplaces::parse::{parse_number, FieldErrors, FieldParseError};
 
 // ────────────────────────────────────────────────────────────────────────────
 // ymid_ key helpers
@@ -104,6 +105,8 @@ pub struct ParsedPaymentReport {
     pub entries: Vec<YmPaymentReportEntry>,
     /// Rows skipped during parsing.
     pub skipped: i32,
+    /// Numeric fields present in the CSV but not parseable (stored as NULL).
+    pub field_errors: Vec<FieldParseError>,
 }
 
 /// Parse YM payment-report CSV text into upsert-ready entries.
@@ -120,6 +123,7 @@ pub fn parse_payment_report_csv(
     let connection_mp_ref = connection.base.id.as_string();
 
     let mut skipped = 0i32;
+    let mut field_errors = FieldErrors::default();
 
     // De-duplicate by record_key, keeping the last occurrence (matching the old
     // per-row "last upsert wins" behaviour). Order is irrelevant: upsert is
@@ -170,12 +174,26 @@ pub fn parse_payment_report_csv(
         // Real YM transaction ID — may be empty; stored as data but never used as key.
         let real_transaction_id = get_field("TRANSACTION_ID");
 
+        // Numeric fields: an unparseable value is stored as NULL and reported.
+        let mut row_errors = FieldErrors::default();
+        let mut integer = |name: &str| row_errors.integer(name, get_field(name).as_deref());
+        let order_id_num = integer("ORDER_ID");
+        let business_id = integer("BUSINESS_ID");
+        let partner_id = integer("PARTNER_ID");
+        let count = integer("COUNT").map(|v| v as i32);
+        let act_id = integer("ACT_ID");
+        let bank_order_id = integer("BANK_ORDER_ID");
+        let sum_f = row_errors.number("TRANSACTION_SUM", get_field("TRANSACTION_SUM").as_deref());
+        let bank_sum = row_errors.number("BANK_SUM", get_field("BANK_SUM").as_deref());
+        if !row_errors.is_empty() {
+            let line = record.position().map(|p| p.line()).unwrap_or_default();
+            field_errors.extend(&format!("строка {}", line), row_errors.into_vec());
+        }
+
         // Fields used for key construction — parsed/raw forms.
-        let order_id_num = get_field("ORDER_ID").and_then(|v| v.parse::<i64>().ok());
         let date_raw = get_field("TRANSACTION_DATE").unwrap_or_default();
         let typ_raw = get_field("TRANSACTION_TYPE").unwrap_or_default();
         let sku_raw = get_field("SHOP_SKU").unwrap_or_default();
-        let sum_f = get_field("TRANSACTION_SUM").and_then(|v| parse_decimal(&v));
 
         if order_id_num.is_none() && date_raw.is_empty() {
             tracing::warn!(
@@ -198,8 +216,8 @@ pub fn parse_payment_report_csv(
             connection_mp_ref: connection_mp_ref.clone(),
             organization_ref: organization_id.to_string(),
 
-            business_id: get_field("BUSINESS_ID").and_then(|v| v.parse::<i64>().ok()),
-            partner_id: get_field("PARTNER_ID").and_then(|v| v.parse::<i64>().ok()),
+            business_id,
+            partner_id,
             shop_name: get_field("SHOP_NAME"),
             inn: get_field("INN"),
             model: get_field("MODEL"),
@@ -219,13 +237,13 @@ pub fn parse_payment_report_csv(
 
             shop_sku: get_field("SHOP_SKU"),
             offer_or_service_name: get_field("OFFER_OR_SERVICE_NAME"),
-            count: get_field("COUNT").and_then(|v| v.parse::<i32>().ok()),
+            count,
 
-            act_id: get_field("ACT_ID").and_then(|v| v.parse::<i64>().ok()),
+            act_id,
             act_date: get_field("ACT_DATE").map(|d| ru_date_to_iso(&d)),
-            bank_order_id: get_field("BANK_ORDER_ID").and_then(|v| v.parse::<i64>().ok()),
+            bank_order_id,
             bank_order_date: get_field("BANK_ORDER_DATE").map(|d| ru_date_to_iso(&d)),
-            bank_sum: get_field("BANK_SUM").and_then(|v| parse_decimal(&v)),
+            bank_sum,
 
             claim_number: get_field("CLAIM_NUMBER"),
             bonus_account_year_month: get_field("BONUS_ACCOUNT_YEAR_MONTH"),
@@ -245,17 +263,21 @@ pub fn parse_payment_report_csv(
         skipped
     );
 
-    Ok(ParsedPaymentReport { entries, skipped })
+    Ok(ParsedPaymentReport {
+        entries,
+        skipped,
+        field_errors: field_errors.into_vec(),
+    })
 }
 
 // ────────────────────────────────────────────────────────────────────────────
 // Helpers (also used by repository::migrate_synth_keys via public export)
 // ────────────────────────────────────────────────────────────────────────────
 
-/// Parse decimal number that may use comma as decimal separator (European format)
+/// Parse decimal number: comma or dot decimal separator, spaces as thousands
+/// separators (see `shared::marketplaces::parse::parse_number`).
 pub fn parse_decimal(s: &str) -> Option<f64> {
-    let normalized = s.replace(',', ".");
-    normalized.parse::<f64>().ok()
+    parse_number(s)
 }
 
 /// Convert Russian date format DD.MM.YYYY HH:MM to ISO YYYY-MM-DD HH:MM.
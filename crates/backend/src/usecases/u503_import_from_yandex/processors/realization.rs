@@ -18,7 +18,7 @@ use contracts::domain::a034_ym_realization::aggregate::{
 use contracts::domain::common::AggregateId;
 use std::collections::BTreeMap;
 
-use super::payment_report::{parse_decimal, ru_date_to_iso};
+use crate::shared::marketplaces::parse::{parse_date, FieldErrors, FieldParseError};
 
 /// Описание одного файла отчёта: имена колонок-кандидатов (калибруются под
 /// реальную выгрузку — у delivered/returned разные имена колонок суммы).
@@ -78,6 +78,8 @@ const SPECS: &[FileSpec] = &[
 pub struct ParsedRealization {
     pub documents: Vec<YmRealization>,
     pub skipped: i32,
+    /// Неразобранные суммы/количества/даты (строка с такой суммой пропускается).
+    pub field_errors: Vec<FieldParseError>,
 }
 
 /// Строки одного дня, разнесённые по типу (продажи / возвраты).
@@ -87,16 +89,6 @@ struct DayLines {
     returns: Vec<YmRealizationLine>,
 }
 
-/// Очистка денежной строки YM: убираем пробелы/неразрывные пробелы (разделители
-/// тысяч), затем парсим с учётом запятой как десятичного разделителя.
-fn parse_money(raw: &str) -> Option<f64> {
-    let cleaned: String = raw
-        .chars()
-        .filter(|c| !c.is_whitespace() && *c != '\u{00a0}')
-        .collect();
-    parse_decimal(&cleaned)
-}
-
 /// Привязка даты строки к месяцу отчёта: вне диапазона или пустая → конец месяца.
 fn clamp_to_month(day: Option<String>, month_first: &str, month_last: &str) -> String {
     match day {
@@ -119,6 +111,7 @@ pub fn parse_realization_files(
     let fetched_at = chrono::Utc::now().to_rfc3339();
 
     let mut skipped = 0i32;
+    let mut field_errors = FieldErrors::default();
     // Продажи и возвраты разносятся в отдельные коллекции уже на парсинге —
     // приходят из разных файлов (delivered/returned) и не смешиваются.
     let mut by_day: BTreeMap<String, DayLines> = BTreeMap::new();
@@ -138,6 +131,7 @@ pub fn parse_realization_files(
             month_last,
             &mut by_day,
             &mut skipped,
+            &mut field_errors,
         );
     }
 
@@ -159,8 +153,9 @@ pub fn parse_realization_files(
                 fetched_at: fetched_at.clone(),
             },
         );
-        document.is_posted = true;
-        document.base.metadata.is_posted = true;
+        document.is_posted = !connection.is_sandbox;
+        document.base.metadata.is_posted = !connection.is_sandbox;
+        document.is_sandbox = connection.is_sandbox;
         documents.push(document);
     }
 
@@ -169,7 +164,11 @@ pub fn parse_realization_files(
         documents.len(),
         skipped
     );
-    Ok(ParsedRealization { documents, skipped })
+    Ok(ParsedRealization {
+        documents,
+        skipped,
+        field_errors: field_errors.into_vec(),
+    })
 }
 
 fn parse_file(
@@ -179,6 +178,7 @@ fn parse_file(
     month_last: &str,
     by_day: &mut BTreeMap<String, DayLines>,
     skipped: &mut i32,
+    field_errors: &mut FieldErrors,
 ) {
     let text = csv_text.trim_start_matches('\u{FEFF}');
     let mut reader = csv::ReaderBuilder::new()
@@ -236,20 +236,49 @@ fn parse_file(
                 .filter(|v| !v.is_empty())
         };
 
-        let row_day = date_indices.iter().find_map(|&i| {
-            record
-                .get(i)
-                .map(str::trim)
-                .filter(|v| !v.is_empty())
-                .map(ru_date_to_iso)
-                .filter(|iso| iso.len() >= 10)
-                .map(|iso| iso.chars().take(10).collect::<String>())
-        });
+        let mut row_errors = FieldErrors::default();
+        let column = |idx: Option<usize>| idx.and_then(|i| headers.get(i)).unwrap_or_default();
+        let date_values: Vec<(usize, &str)> = date_indices
+            .iter()
+            .filter_map(|&i| {
+                record
+                    .get(i)
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .map(|v| (i, v))
+            })
+            .collect();
+        let row_day = date_values
+            .iter()
+            .find_map(|(_, v)| parse_date(v))
+            .map(|d| d.format("%Y-%m-%d").to_string());
+        if row_day.is_none() {
+            // Ни одна из колонок даты не разобрана — строка уйдёт на конец месяца
+            if let Some(&(i, raw)) = date_values.first() {
+                row_errors.date(column(Some(i)), Some(raw));
+            }
+        }
         let day = clamp_to_month(row_day, month_first, month_last);
 
-        let revenue = get(idx_revenue)
-            .and_then(|v| parse_money(&v))
-            .unwrap_or(0.0);
+        // Пустая сумма/количество — ноль; нечисловое значение — ошибка, строка пропускается
+        let revenue_raw = get(idx_revenue);
+        let revenue = row_errors.number(column(idx_revenue), revenue_raw.as_deref());
+        let quantity_raw = get(idx_quantity);
+        let quantity = row_errors.number(column(idx_quantity), quantity_raw.as_deref());
+        let unparsed = (revenue_raw.is_some() && revenue.is_none())
+            || (quantity_raw.is_some() && quantity.is_none());
+        if !row_errors.is_empty() {
+            let line = record.position().map(|p| p.line()).unwrap_or_default();
+            field_errors.extend(
+                &format!("{} строка {}", spec.filename, line),
+                row_errors.into_vec(),
+            );
+        }
+        if unparsed {
+            *skipped += 1;
+            continue;
+        }
+        let revenue = revenue.unwrap_or(0.0);
 
         let line = YmRealizationLine {
             order_id: get(idx_order),
@@ -258,9 +287,7 @@ fn parse_file(
             marketplace_product_ref: None,
             market_sku: None,
             offer_name: get(idx_offer_name).unwrap_or_default(),
-            quantity: get(idx_quantity)
-                .and_then(|v| parse_money(&v))
-                .unwrap_or(0.0),
+            quantity: quantity.unwrap_or(0.0),
             // Выручка хранится положительной; знак операции несёт is_return.
             revenue_amount: revenue.abs(),
             is_return: spec.is_return,
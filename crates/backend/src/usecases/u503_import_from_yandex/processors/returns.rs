@@ -103,7 +103,7 @@ pub async fn process_return(
     };
 
     let return_type_display = return_item.return_type.clone().unwrap_or_default();
-    let document = YmReturn::new_for_insert(
+    let mut document = YmReturn::new_for_insert(
         return_id_str.clone(),
         format!(
             "YM {} {} (Order {})",
@@ -119,8 +119,9 @@ pub async fn process_return(
         lines,
         state,
         source_meta,
-        true,
+        !connection.is_sandbox,
     );
+    document.is_sandbox = connection.is_sandbox;
 
     let raw_json = serde_json::to_string(&return_item)?;
     a016_ym_returns::service::store_document_with_raw(document, &raw_json).await?;
@@ -1,3 +1,4 @@
+use crate::shared::marketplaces::sandbox;
 use anyhow::Result;
 use contracts::domain::a006_connection_mp::aggregate::{AuthorizationType, ConnectionMP};
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT};
@@ -127,10 +128,13 @@ impl YandexApiClient {
             anyhow::bail!("Yandex Market API token is required");
         }
 
-        let url = format!(
-            "https://api.partner.market.yandex.ru/v2/businesses/{}/offer-mappings",
-            business_id
-        );
+        let url = sandbox::endpoint(
+            connection,
+            &format!(
+                "https://api.partner.market.yandex.ru/v2/businesses/{}/offer-mappings",
+                business_id
+            ),
+        )?;
 
         // Отправляем пагинацию через query-параметры, так как тело может игнорироваться API для этого эндпоинта
         #[derive(Serialize)]
@@ -255,10 +259,13 @@ impl YandexApiClient {
             anyhow::bail!("Yandex Market API token is required");
         }
 
-        let url = format!(
-            "https://api.partner.market.yandex.ru/v2/businesses/{}/offer-cards",
-            business_id
-        );
+        let url = sandbox::endpoint(
+            connection,
+            &format!(
+                "https://api.partner.market.yandex.ru/v2/businesses/{}/offer-cards",
+                business_id
+            ),
+        )?;
 
         let request_body = YandexProductInfoRequest { offer_ids };
 
@@ -630,10 +637,13 @@ impl YandexApiClient {
             anyhow::bail!("Yandex Market API token is required");
         }
 
-        let url = format!(
-            "https://api.partner.market.yandex.ru/campaigns/{}/orders",
-            campaign_id
-        );
+        let url = sandbox::endpoint(
+            connection,
+            &format!(
+                "https://api.partner.market.yandex.ru/campaigns/{}/orders",
+                campaign_id
+            ),
+        )?;
 
         // Параметры даты зависят от выбранного поля фильтрации.
         // Creation: fromDate/toDate в формате DD-MM-YYYY.
@@ -737,10 +747,13 @@ impl YandexApiClient {
             anyhow::bail!("Yandex Market API token is required");
         }
 
-        let url = format!(
-            "https://api.partner.market.yandex.ru/campaigns/{}/orders/{}",
-            campaign_id, order_id
-        );
+        let url = sandbox::endpoint(
+            connection,
+            &format!(
+                "https://api.partner.market.yandex.ru/campaigns/{}/orders/{}",
+                campaign_id, order_id
+            ),
+        )?;
 
         self.log_to_file(&format!(
             "=== REQUEST ===\nGET {}\n{}",
@@ -814,7 +827,7 @@ impl YandexApiClient {
             anyhow::bail!("Yandex Market API token is required");
         }
 
-        let url = "https://api.partner.market.yandex.ru/campaigns";
+        let url = &sandbox::endpoint(connection, "https://api.partner.market.yandex.ru/campaigns")?;
         let page_size = 50;
         let mut all = Vec::new();
         let mut page = 1;
@@ -1282,10 +1295,13 @@ impl YandexApiClient {
             anyhow::bail!("Yandex Market API token is required");
         }
 
-        let url = format!(
-            "https://api.partner.market.yandex.ru/v2/campaigns/{}/returns",
-            campaign_id
-        );
+        let url = sandbox::endpoint(
+            connection,
+            &format!(
+                "https://api.partner.market.yandex.ru/v2/campaigns/{}/returns",
+                campaign_id
+            ),
+        )?;
 
         // Build query parameters
         // Per Yandex Market API: dates are YYYY-MM-DD, page size is `limit`,
@@ -1386,8 +1402,10 @@ impl YandexApiClient {
             .parse()
             .map_err(|e| anyhow::anyhow!("businessId must be an integer: {}", e))?;
 
-        let url =
-            "https://api.partner.market.yandex.ru/v2/reports/united-netting/generate?format=CSV";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://api.partner.market.yandex.ru/v2/reports/united-netting/generate?format=CSV",
+        )?;
 
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
@@ -1473,8 +1491,10 @@ impl YandexApiClient {
             .parse()
             .map_err(|e| anyhow::anyhow!("campaignId must be an integer: {}", e))?;
 
-        let url =
-            "https://api.partner.market.yandex.ru/v2/reports/goods-realization/generate?format=CSV";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://api.partner.market.yandex.ru/v2/reports/goods-realization/generate?format=CSV",
+        )?;
 
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
@@ -1578,10 +1598,13 @@ impl YandexApiClient {
         connection: &ConnectionMP,
         report_id: &str,
     ) -> Result<(String, Option<String>)> {
-        let url = format!(
-            "https://api.partner.market.yandex.ru/v2/reports/info/{}",
-            report_id
-        );
+        let url = sandbox::endpoint(
+            connection,
+            &format!(
+                "https://api.partner.market.yandex.ru/v2/reports/info/{}",
+                report_id
+            ),
+        )?;
 
         let request = self.client.get(&url);
 
@@ -0,0 +1,165 @@
+//! Контрольные точки подневной загрузки (финансовый отчёт WB: 1 запрос/мин, диапазон
+//! может грузиться часами). После каждого обработанного дня фиксируется
+//! `completed_through`; если загрузка упала, следующий запуск с тем же началом
+//! диапазона продолжает со следующего дня. Точка удаляется после успешного завершения
+//! и игнорируется, если устарела.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+use crate::shared::data::db::get_connection;
+
+/// Сколько живёт контрольная точка: более старые данные лучше перезагрузить целиком.
+const CHECKPOINT_TTL_HOURS: i64 = 48;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportCheckpoint {
+    pub range_from: NaiveDate,
+    pub range_to: NaiveDate,
+    pub completed_through: NaiveDate,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// С какого дня продолжать загрузку диапазона `date_from..=date_to`.
+pub fn resume_date(
+    checkpoint: Option<&ImportCheckpoint>,
+    date_from: NaiveDate,
+    date_to: NaiveDate,
+    now: DateTime<Utc>,
+) -> NaiveDate {
+    match checkpoint {
+        Some(cp)
+            if cp.range_from == date_from
+                && now - cp.updated_at <= Duration::hours(CHECKPOINT_TTL_HOURS)
+                && cp.completed_through >= date_from
+                && cp.completed_through < date_to =>
+        {
+            cp.completed_through + Duration::days(1)
+        }
+        _ => date_from,
+    }
+}
+
+pub async fn load(scope: &str) -> Result<Option<ImportCheckpoint>> {
+    let row = get_connection()
+        .query_one(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "SELECT range_from, range_to, completed_through, updated_at
+             FROM sys_import_checkpoints WHERE scope = ?",
+            [scope.into()],
+        ))
+        .await?;
+    let Some(row) = row else {
+        return Ok(None);
+    };
+    let date = |column: &str| -> Option<NaiveDate> {
+        let raw: String = row.try_get("", column).ok()?;
+        NaiveDate::parse_from_str(&raw, "%Y-%m-%d").ok()
+    };
+    let updated_at = row
+        .try_get::<String>("", "updated_at")
+        .ok()
+        .and_then(|raw| DateTime::parse_from_rfc3339(&raw).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+    Ok(
+        match (
+            date("range_from"),
+            date("range_to"),
+            date("completed_through"),
+            updated_at,
+        ) {
+            (Some(range_from), Some(range_to), Some(completed_through), Some(updated_at)) => {
+                Some(ImportCheckpoint {
+                    range_from,
+                    range_to,
+                    completed_through,
+                    updated_at,
+                })
+            }
+            _ => None,
+        },
+    )
+}
+
+pub async fn save(
+    scope: &str,
+    range_from: NaiveDate,
+    range_to: NaiveDate,
+    completed_through: NaiveDate,
+) -> Result<()> {
+    get_connection()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT INTO sys_import_checkpoints (scope, range_from, range_to, completed_through, updated_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(scope) DO UPDATE SET
+                range_from = excluded.range_from,
+                range_to = excluded.range_to,
+                completed_through = excluded.completed_through,
+                updated_at = excluded.updated_at",
+            [
+                scope.into(),
+                range_from.format("%Y-%m-%d").to_string().into(),
+                range_to.format("%Y-%m-%d").to_string().into(),
+                completed_through.format("%Y-%m-%d").to_string().into(),
+                Utc::now().to_rfc3339().into(),
+            ],
+        ))
+        .await?;
+    Ok(())
+}
+
+pub async fn clear(scope: &str) -> Result<()> {
+    get_connection()
+        .execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "DELETE FROM sys_import_checkpoints WHERE scope = ?",
+            [scope.into()],
+        ))
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn checkpoint(range_from: &str, completed_through: &str, age_hours: i64) -> ImportCheckpoint {
+        ImportCheckpoint {
+            range_from: d(range_from),
+            range_to: d("2026-03-20"),
+            completed_through: d(completed_through),
+            updated_at: Utc::now() - Duration::hours(age_hours),
+        }
+    }
+
+    #[test]
+    fn resumes_after_last_completed_day() {
+        let cp = checkpoint("2026-03-01", "2026-03-05", 1);
+        assert_eq!(
+            resume_date(Some(&cp), d("2026-03-01"), d("2026-03-20"), Utc::now()),
+            d("2026-03-06")
+        );
+    }
+
+    #[test]
+    fn ignores_foreign_stale_or_finished_checkpoints() {
+        let now = Utc::now();
+        let (from, to) = (d("2026-03-01"), d("2026-03-20"));
+        assert_eq!(resume_date(None, from, to, now), from);
+        // Другое начало диапазона
+        let other = checkpoint("2026-02-25", "2026-03-05", 1);
+        assert_eq!(resume_date(Some(&other), from, to, now), from);
+        // Устаревшая точка
+        let stale = checkpoint("2026-03-01", "2026-03-05", CHECKPOINT_TTL_HOURS + 1);
+        assert_eq!(resume_date(Some(&stale), from, to, now), from);
+        // Диапазон уже пройден до конца
+        let done = checkpoint("2026-03-01", "2026-03-20", 1);
+        assert_eq!(resume_date(Some(&done), from, to, now), from);
+    }
+}
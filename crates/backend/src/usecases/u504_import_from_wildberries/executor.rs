@@ -1,6 +1,7 @@
 #[allow(unused_imports)]
 use super::wildberries_api_client::WbMarketplaceOrderRow;
 use super::{
+    checkpoint,
     processors::{
         commission, document, finance_report, goods_prices, marketplace_order, order, product,
         promotion, sales, supply,
@@ -12,6 +13,9 @@ use super::{
         WbSearchReportRow, WildberriesApiClient,
     },
 };
+use crate::shared::data::raw_storage;
+use crate::shared::marketplaces::parse::{report_summary, FieldErrors};
+use crate::shared::marketplaces::sandbox;
 use crate::shared::marketplaces::wildberries::datetime::{wb_day_end_utc, wb_day_start_utc};
 use anyhow::{Context, Result};
 use contracts::domain::a026_wb_advert_daily::aggregate::{
@@ -408,7 +412,22 @@ impl ImportExecutor {
         let _http_tracking = self
             .api_client
             .bind_http_tracking(Arc::clone(&self.progress_tracker), session_id.to_string());
-        let work_result = self.run_aggregates(session_id, request, connection).await;
+        let (work_result, raw_stats) =
+            raw_storage::with_dedup_stats(self.run_aggregates(session_id, request, connection))
+                .await;
+        if raw_stats != raw_storage::RawDedupStats::default() {
+            tracing::info!(
+                "WB import {}: raw JSON stored {}, deduplicated {}",
+                session_id,
+                raw_stats.stored,
+                raw_stats.deduplicated
+            );
+            self.progress_tracker.record_raw_payloads(
+                session_id,
+                raw_stats.stored,
+                raw_stats.deduplicated,
+            );
+        }
 
         // Трекер ВСЕГДА получает финальный статус — не только в happy path.
         let final_status = match &work_result {
@@ -458,6 +477,21 @@ impl ImportExecutor {
     ) -> Result<ImportRunFlags> {
         let mut flags = ImportRunFlags::default();
         for aggregate_index in &request.target_aggregates {
+            if sandbox::skips_import(connection, aggregate_index) {
+                tracing::info!(
+                    "Import of {} skipped: connection {} is in sandbox mode",
+                    aggregate_index,
+                    connection.base.id.as_string()
+                );
+                self.progress_tracker.set_current_item(
+                    session_id,
+                    aggregate_index,
+                    Some("Пропущено: подключение в режиме песочницы".to_string()),
+                );
+                self.progress_tracker
+                    .complete_aggregate(session_id, aggregate_index);
+                continue;
+            }
             match aggregate_index.as_str() {
                 "a007_marketplace_product" => {
                     self.import_marketplace_products(session_id, connection)
@@ -829,6 +863,7 @@ impl ImportExecutor {
             crate::domain::a012_wb_sales::service::PostingPreparationCache::default();
 
         // Обрабатываем каждую продажу
+        let mut field_errors = FieldErrors::default();
         for (sale_row, raw_json) in sales_rows {
             match sales::process_sale_row(
                 connection,
@@ -837,6 +872,8 @@ impl ImportExecutor {
                 &raw_json,
                 &existing_sale_ids,
                 &mut shared_cache,
+                session_id,
+                &mut field_errors,
             )
             .await
             {
@@ -869,6 +906,16 @@ impl ImportExecutor {
             );
         }
 
+        if let Some((message, details)) = report_summary(&field_errors.into_vec()) {
+            tracing::warn!("WB sales import: {}", message);
+            self.progress_tracker.add_error(
+                session_id,
+                Some(aggregate_index.to_string()),
+                message,
+                Some(details),
+            );
+        }
+
         self.progress_tracker
             .set_current_item(session_id, aggregate_index, None);
         self.progress_tracker
@@ -981,8 +1028,17 @@ impl ImportExecutor {
         );
 
         // Обрабатываем каждый заказ
+        let mut field_errors = FieldErrors::default();
         for order_row in order_rows {
-            match order::process_order_row(connection, &organization_id, &order_row).await {
+            match order::process_order_row(
+                connection,
+                &organization_id,
+                &order_row,
+                session_id,
+                &mut field_errors,
+            )
+            .await
+            {
                 Ok(is_new) => {
                     total_processed += 1;
                     if is_new {
@@ -1012,6 +1068,16 @@ impl ImportExecutor {
             );
         }
 
+        if let Some((message, details)) = report_summary(&field_errors.into_vec()) {
+            tracing::warn!("WB orders import: {}", message);
+            self.progress_tracker.add_error(
+                session_id,
+                Some(aggregate_index.to_string()),
+                message,
+                Some(details),
+            );
+        }
+
         self.progress_tracker
             .set_current_item(session_id, aggregate_index, None);
         self.progress_tracker
@@ -1725,7 +1791,8 @@ impl ImportExecutor {
     /// Импорт финансовых отчетов Wildberries из API в p903_wb_finance_report
     ///
     /// ВАЖНО: API reportDetailByPeriod имеет лимит 1 запрос в минуту!
-    /// Данные загружаются за весь период с пагинацией, а не по дням.
+    /// Данные загружаются по дням; после каждого дня сохраняется контрольная точка,
+    /// и повторный запуск того же диапазона продолжает с места обрыва.
     async fn import_wb_finance_report(
         &self,
         session_id: &str,
@@ -1794,7 +1861,42 @@ impl ImportExecutor {
         );
 
         let total_days = (date_to - date_from).num_days() as i32 + 1;
-        let mut current_date = date_from;
+
+        // Продолжаем с последнего обработанного дня, если прошлый запуск того же
+        // диапазона оборвался (таймаут, обрыв связи)
+        let checkpoint_scope = format!("{}:{}", aggregate_index, connection.to_string_id());
+        let stored_checkpoint = checkpoint::load(&checkpoint_scope)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load finance import checkpoint: {}", e);
+                None
+            });
+        let mut current_date = checkpoint::resume_date(
+            stored_checkpoint.as_ref(),
+            date_from,
+            date_to,
+            chrono::Utc::now(),
+        );
+        if current_date > date_from {
+            processed_days = (current_date - date_from).num_days() as i32;
+            tracing::info!(
+                "Resuming WB finance report import from {} (days {}..{} already loaded)",
+                current_date,
+                date_from,
+                current_date - chrono::Duration::days(1)
+            );
+            self.progress_tracker.update_aggregate(
+                session_id,
+                aggregate_index,
+                processed_days,
+                Some(total_days),
+                0,
+                0,
+            );
+        }
+        // После дня с ошибкой сверки точку не двигаем: его нужно перегрузить
+        let mut checkpoint_valid = true;
+
         while current_date <= date_to {
             self.progress_tracker.set_current_item(
                 session_id,
@@ -1850,6 +1952,7 @@ impl ImportExecutor {
                     total_gl_rows += result.general_ledger_rows as i32;
                 }
                 Err(e) => {
+                    checkpoint_valid = false;
                     tracing::error!(
                         "Failed to reconcile finance report day {}: {}",
                         current_date,
@@ -1867,6 +1970,14 @@ impl ImportExecutor {
                 }
             }
 
+            if checkpoint_valid {
+                if let Err(e) =
+                    checkpoint::save(&checkpoint_scope, date_from, date_to, current_date).await
+                {
+                    tracing::warn!("Failed to save finance import checkpoint: {}", e);
+                }
+            }
+
             processed_days += 1;
             self.progress_tracker.update_aggregate(
                 session_id,
@@ -1880,6 +1991,12 @@ impl ImportExecutor {
             current_date += chrono::Duration::days(1);
         }
 
+        if checkpoint_valid {
+            if let Err(e) = checkpoint::clear(&checkpoint_scope).await {
+                tracing::warn!("Failed to clear finance import checkpoint: {}", e);
+            }
+        }
+
         self.progress_tracker.update_aggregate(
             session_id,
             aggregate_index,
@@ -2914,7 +3031,7 @@ impl ImportExecutor {
             end_date,
             document_ids.len()
         );
-        for document_id in &document_ids {
+        for document_id in document_ids.iter().filter(|_| !connection.is_sandbox) {
             post_wb_advert_document_with_retry(*document_id)
                 .await
                 .with_context(|| {
@@ -3155,7 +3272,7 @@ impl ImportExecutor {
         .await
         .context("Failed to store backfill advert documents")?;
 
-        for document_id in &document_ids {
+        for document_id in document_ids.iter().filter(|_| !connection.is_sandbox) {
             crate::domain::a026_wb_advert_daily::posting::post_document(*document_id)
                 .await
                 .with_context(|| {
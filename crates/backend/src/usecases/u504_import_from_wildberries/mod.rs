@@ -1,3 +1,4 @@
+pub mod checkpoint;
 pub mod executor;
 pub mod processors;
 pub mod progress_tracker;
@@ -13,10 +13,6 @@ pub async fn map_finance_report_row(
         anyhow::bail!("Missing rrd_id or rr_dt");
     }
 
-    let rrd_id = row.rrd_id.unwrap();
-    let rr_dt_str = row.rr_dt.clone().unwrap();
-    let rr_dt = chrono::NaiveDate::parse_from_str(&rr_dt_str, "%Y-%m-%d")?;
-    let extra_json = serde_json::to_string(row).ok();
     let a004_nomenclature_ref = match row.nm_id.filter(|v| *v > 0) {
         Some(nm_id) => {
             // Если a007 для (connection, nm_id) ещё не существует — создаём его прямо здесь,
@@ -58,11 +54,35 @@ pub async fn map_finance_report_row(
         None => None,
     };
 
+    entry_from_row(
+        &connection.base.id.as_string(),
+        organization_id,
+        row,
+        a004_nomenclature_ref,
+    )
+}
+
+/// Запись p903 из строки отчёта без обращения к БД.
+///
+/// `a004_nomenclature_ref` передаётся снаружи: обычный импорт разрешает его через
+/// a007, массовая загрузка истории оставляет пустым (заполнит перепроведение).
+pub fn entry_from_row(
+    connection_mp_ref: &str,
+    organization_id: &str,
+    row: &WbFinanceReportRow,
+    a004_nomenclature_ref: Option<String>,
+) -> Result<WbFinanceReportEntry> {
+    let (Some(rrd_id), Some(rr_dt_str)) = (row.rrd_id, row.rr_dt.as_deref()) else {
+        anyhow::bail!("Missing rrd_id or rr_dt");
+    };
+    let rr_dt = chrono::NaiveDate::parse_from_str(rr_dt_str, "%Y-%m-%d")?;
+    let extra_json = serde_json::to_string(row).ok();
+
     Ok(WbFinanceReportEntry {
         rr_dt,
         rrd_id,
         source_row_ref: repository::make_source_row_ref(rrd_id),
-        connection_mp_ref: connection.base.id.as_string(),
+        connection_mp_ref: connection_mp_ref.to_string(),
         organization_ref: organization_id.to_string(),
         acquiring_fee: row.acquiring_fee,
         acquiring_percent: row.acquiring_percent,
@@ -0,0 +1,128 @@
+//! Массовая загрузка истории финансового отчёта WB (p903) из NDJSON.
+//!
+//! Строки файла — объекты в формате ответа `reportDetailByPeriod`. Каждая строка
+//! проверяется и копится в пачку; полная пачка пишется одним multi-row upsert.
+//! В отличие от обычного импорта a007/a004 не разрешаются построчно: ссылки
+//! заполняет перепроведение загруженного периода (`rebuild_range_from_existing`).
+
+use super::super::wildberries_api_client::WbFinanceReportRow;
+use super::finance_report::entry_from_row;
+use crate::projections::p903_wb_finance_report::repository::{self, WbFinanceReportEntry};
+use chrono::NaiveDate;
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::common::AggregateId;
+use contracts::system::bulk_import::{BulkImportErrorDto, BulkImportResultDto};
+
+/// Строк в одном INSERT: ~40 колонок × 500 укладывается в лимит переменных SQLite.
+pub const CHUNK_SIZE: usize = 500;
+/// Больше ошибок в ответ не кладём — остальные только считаются.
+const MAX_ERRORS: usize = 200;
+
+pub struct FinanceReportBulkImport {
+    connection_mp_ref: String,
+    organization_ref: String,
+    chunk: Vec<WbFinanceReportEntry>,
+    /// Строка файла, с которой началась текущая пачка.
+    chunk_first_line: usize,
+    line_no: usize,
+    date_from: Option<NaiveDate>,
+    date_to: Option<NaiveDate>,
+    result: BulkImportResultDto,
+}
+
+impl FinanceReportBulkImport {
+    pub fn new(connection: &ConnectionMP) -> Self {
+        Self {
+            connection_mp_ref: connection.base.id.as_string(),
+            organization_ref: connection.organization_ref.clone(),
+            chunk: Vec::with_capacity(CHUNK_SIZE),
+            chunk_first_line: 0,
+            line_no: 0,
+            date_from: None,
+            date_to: None,
+            result: BulkImportResultDto::default(),
+        }
+    }
+
+    /// Принять строку файла; при заполнении пачки она сразу пишется в БД.
+    pub async fn push_line(&mut self, line: &str) {
+        self.line_no += 1;
+        self.result.total_lines += 1;
+        match self.parse_line(line) {
+            Ok(entry) => {
+                if self.chunk.is_empty() {
+                    self.chunk_first_line = self.line_no;
+                }
+                self.chunk.push(entry);
+                if self.chunk.len() >= CHUNK_SIZE {
+                    self.flush().await;
+                }
+            }
+            Err(message) => {
+                self.result.rejected += 1;
+                self.error(self.result.chunks, self.line_no, message);
+            }
+        }
+    }
+
+    /// Ошибка потока (обрыв, повреждённый gzip): строки после неё не прочитаны.
+    pub fn stream_error(&mut self, message: String) {
+        self.error(self.result.chunks, self.line_no + 1, message);
+    }
+
+    /// Записать остаток и вернуть итог.
+    pub async fn finish(mut self) -> BulkImportResultDto {
+        self.flush().await;
+        self.result.date_from = self.date_from.map(|d| d.format("%Y-%m-%d").to_string());
+        self.result.date_to = self.date_to.map(|d| d.format("%Y-%m-%d").to_string());
+        self.result
+    }
+
+    fn parse_line(&self, line: &str) -> Result<WbFinanceReportEntry, String> {
+        let row: WbFinanceReportRow =
+            serde_json::from_str(line).map_err(|e| format!("Некорректный JSON: {}", e))?;
+        entry_from_row(&self.connection_mp_ref, &self.organization_ref, &row, None)
+            .map_err(|e| format!("Строка не прошла проверку: {}", e))
+    }
+
+    async fn flush(&mut self) {
+        if self.chunk.is_empty() {
+            return;
+        }
+        let entries = std::mem::replace(&mut self.chunk, Vec::with_capacity(CHUNK_SIZE));
+        let index = self.result.chunks;
+        self.result.chunks += 1;
+        match repository::upsert_entries_batch(&entries).await {
+            Ok(_) => {
+                self.result.upserted += entries.len();
+                for entry in &entries {
+                    self.date_from =
+                        Some(self.date_from.map_or(entry.rr_dt, |d| d.min(entry.rr_dt)));
+                    self.date_to = Some(self.date_to.map_or(entry.rr_dt, |d| d.max(entry.rr_dt)));
+                }
+            }
+            Err(e) => {
+                tracing::warn!("p903 bulk import: chunk {} failed: {}", index, e);
+                self.result.rejected += entries.len();
+                let first = self.chunk_first_line;
+                self.error(
+                    index,
+                    first,
+                    format!("Пачка строк {}–{} не записана: {}", first, self.line_no, e),
+                );
+            }
+        }
+    }
+
+    fn error(&mut self, chunk: usize, line: usize, message: String) {
+        if self.result.errors.len() >= MAX_ERRORS {
+            self.result.errors_truncated = true;
+            return;
+        }
+        self.result.errors.push(BulkImportErrorDto {
+            chunk,
+            line,
+            message,
+        });
+    }
+}
@@ -204,6 +204,7 @@ pub async fn process_marketplace_order(
         marketplace_raw_payload_ref: None,
         fetched_at: chrono::Utc::now(),
         document_version: 1,
+        import_session_id: None,
     };
 
     let description = format!(
@@ -216,7 +217,7 @@ pub async fn process_marketplace_order(
     // к MSK из order_dt, чтобы формат совпадал с заказами из Statistics API.
     let document_date = Some(format_wb_local_datetime_seconds(&order_dt));
 
-    let document = WbOrders::new_for_insert(
+    let mut document = WbOrders::new_for_insert(
         document_no.clone(),
         description,
         header,
@@ -225,9 +226,15 @@ pub async fn process_marketplace_order(
         warehouse,
         geography,
         source_meta,
-        true,
+        !connection.is_sandbox,
         document_date,
     );
+    document.is_sandbox = connection.is_sandbox;
+    if let Some(description) =
+        crate::system::description_templates::service::describe_a015(&document).await
+    {
+        document.base.description = description;
+    }
 
     // Store without raw JSON (marketplace API doesn't provide full analytics payload)
     let raw_json = serde_json::to_string(order)?;
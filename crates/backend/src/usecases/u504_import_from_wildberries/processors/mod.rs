@@ -1,6 +1,7 @@
 pub mod commission;
 pub mod document;
 pub mod finance_report;
+pub mod finance_report_bulk;
 pub mod goods_prices;
 pub mod marketplace_order;
 pub mod order;
@@ -1,5 +1,7 @@
 use super::super::wildberries_api_client::WbOrderRow;
 use crate::domain::a015_wb_orders;
+use crate::shared::marketplaces::field_mapping::a015_wb_orders::MAPPING;
+use crate::shared::marketplaces::parse::FieldErrors;
 use crate::shared::marketplaces::wildberries::datetime::{
     format_wb_local_datetime_seconds, parse_wb_datetime,
 };
@@ -11,15 +13,24 @@ use contracts::domain::a015_wb_orders::aggregate::{
 };
 use contracts::domain::common::AggregateId;
 
+/// Поля заказа читаются по таблице `field_mapping::a015_wb_orders` из raw JSON
+/// строки (включая поля, которых нет в `WbOrderRow`); номер документа — `srid`.
+/// Неразобранные значения полей добавляются в `field_errors` для отчёта импорта.
 pub async fn process_order_row(
     connection: &ConnectionMP,
     organization_id: &str,
     order_row: &WbOrderRow,
+    session_id: &str,
+    field_errors: &mut FieldErrors,
 ) -> Result<bool> {
+    let raw_value = serde_json::to_value(order_row)?;
+    let payload = MAPPING.apply(&raw_value);
+
     let document_no = order_row
         .srid
         .clone()
         .unwrap_or_else(|| format!("WB_ORDER_{}", chrono::Utc::now().timestamp()));
+    field_errors.extend(&document_no, payload.parse_errors());
 
     // Проверяем, существует ли документ
     let existing = a015_wb_orders::service::get_by_document_no(&document_no).await?;
@@ -33,7 +44,7 @@ pub async fn process_order_row(
         marketplace_id: connection.marketplace_id.clone(),
     };
 
-    let supplier_article = order_row.supplier_article.clone().unwrap_or_default();
+    let supplier_article = payload.str("supplier_article").unwrap_or_default();
 
     // Создаем line
     let line = WbOrdersLine {
@@ -42,19 +53,19 @@ pub async fn process_order_row(
             .clone()
             .unwrap_or_else(|| document_no.clone()),
         supplier_article: supplier_article.clone(),
-        nm_id: order_row.nm_id.unwrap_or(0),
-        barcode: order_row.barcode.clone().unwrap_or_default(),
-        category: order_row.category.clone(),
-        subject: order_row.subject.clone(),
-        brand: order_row.brand.clone(),
-        tech_size: order_row.tech_size.clone(),
+        nm_id: payload.i64("nm_id").unwrap_or(0),
+        barcode: payload.str("barcode").unwrap_or_default(),
+        category: payload.str("category"),
+        subject: payload.str("subject"),
+        brand: payload.str("brand"),
+        tech_size: payload.str("tech_size"),
         qty: 1.0,
-        total_price: order_row.total_price,
-        discount_percent: order_row.discount_percent,
-        spp: order_row.spp,
-        finished_price: order_row.finished_price,
-        price_with_disc: order_row.price_with_disc,
-        price: order_row.total_price,
+        total_price: payload.f64("total_price"),
+        discount_percent: payload.f64("discount_percent"),
+        spp: payload.f64("spp"),
+        finished_price: payload.f64("finished_price"),
+        price_with_disc: payload.f64("price_with_disc"),
+        price: payload.f64("total_price"),
         // Statistics API не отдаёт salePrice — придёт позже из Marketplace API.
         sale_price: None,
         dealer_price_ut: None,
@@ -65,52 +76,51 @@ pub async fn process_order_row(
     };
 
     // Парсим даты
-    let order_dt = if let Some(date_str) = order_row.date.as_ref() {
-        parse_wb_datetime(date_str).unwrap_or_else(chrono::Utc::now)
+    let order_dt = if let Some(date_str) = payload.str("order_dt") {
+        parse_wb_datetime(&date_str).unwrap_or_else(chrono::Utc::now)
     } else {
         chrono::Utc::now()
     };
 
-    let last_change_dt = order_row
-        .last_change_date
-        .as_ref()
-        .and_then(|date_str| parse_wb_datetime(date_str));
+    let last_change_dt = payload
+        .str("last_change_dt")
+        .and_then(|date_str| parse_wb_datetime(&date_str));
 
-    let cancel_dt = order_row
-        .cancel_date
-        .as_ref()
-        .and_then(|date_str| parse_wb_datetime(date_str));
+    let cancel_dt = payload
+        .str("cancel_dt")
+        .and_then(|date_str| parse_wb_datetime(&date_str));
 
     let state = WbOrdersState {
         order_dt,
         last_change_dt,
-        is_cancel: order_row.is_cancel.unwrap_or(false),
+        is_cancel: payload.bool("is_cancel").unwrap_or(false),
         cancel_dt,
-        is_supply: order_row.is_supply,
-        is_realization: order_row.is_realization,
+        is_supply: payload.bool("is_supply"),
+        is_realization: payload.bool("is_realization"),
     };
 
     let warehouse = WbOrdersWarehouse {
-        warehouse_name: order_row.warehouse_name.clone(),
-        warehouse_type: order_row.warehouse_type.clone(),
+        warehouse_name: payload.str("warehouse_name"),
+        warehouse_type: payload.str("warehouse_type"),
     };
 
     let geography = WbOrdersGeography {
-        country_name: order_row.country_name.clone(),
-        oblast_okrug_name: order_row.oblast_okrug_name.clone(),
-        region_name: order_row.region_name.clone(),
+        country_name: payload.str("country_name"),
+        oblast_okrug_name: payload.str("oblast_okrug_name"),
+        region_name: payload.str("region_name"),
     };
 
     let source_meta = WbOrdersSourceMeta {
         // incomeID=0 means FBW order with no seller supply — treat as None
-        income_id: order_row.income_id.filter(|&v| v != 0),
+        income_id: payload.i64("income_id").filter(|&v| v != 0),
         // empty sticker string means no sticker assigned — treat as None
-        sticker: order_row.sticker.clone().filter(|s| !s.is_empty()),
-        g_number: order_row.g_number.clone().filter(|s| !s.is_empty()),
+        sticker: payload.str("sticker").filter(|s| !s.is_empty()),
+        g_number: payload.str("g_number").filter(|s| !s.is_empty()),
         raw_payload_ref: String::new(),
         marketplace_raw_payload_ref: None,
         fetched_at: chrono::Utc::now(),
-        document_version: 1,
+        document_version: payload.version() as i32,
+        import_session_id: Some(session_id.to_string()),
     };
 
     let description = format!(
@@ -123,7 +133,7 @@ pub async fn process_order_row(
     // чтобы хранение/фильтрация были единообразны независимо от источника API.
     let document_date = Some(format_wb_local_datetime_seconds(&order_dt));
 
-    let document = WbOrders::new_for_insert(
+    let mut document = WbOrders::new_for_insert(
         document_no.clone(),
         description,
         header,
@@ -132,9 +142,15 @@ pub async fn process_order_row(
         warehouse,
         geography,
         source_meta,
-        true,
+        !connection.is_sandbox,
         document_date,
     );
+    document.is_sandbox = connection.is_sandbox;
+    if let Some(description) =
+        crate::system::description_templates::service::describe_a015(&document).await
+    {
+        document.base.description = description;
+    }
 
     let raw_json = serde_json::to_string(order_row)?;
     a015_wb_orders::service::store_document_with_raw(document, &raw_json).await?;
@@ -58,6 +58,7 @@ pub async fn process_product(connection: &ConnectionMP, card: &WildberriesCard)
         existing_product.before_write();
 
         a007_marketplace_product::repository::update(&existing_product).await?;
+        save_dimensions(&existing_product.to_string_id(), card).await?;
         Ok(false)
     } else {
         // Создаем новый товар
@@ -84,6 +85,21 @@ pub async fn process_product(connection: &ConnectionMP, card: &WildberriesCard)
             a007_marketplace_product::service::search_and_set_nomenclature(&mut new_product).await;
 
         a007_marketplace_product::repository::insert(&new_product).await?;
+        save_dimensions(&new_product.to_string_id(), card).await?;
         Ok(true)
     }
 }
+
+/// Габариты из карточки — для распределения хранения по объёму (p918).
+async fn save_dimensions(product_id: &str, card: &WildberriesCard) -> Result<()> {
+    let Some(dimensions) = card.dimensions.as_ref() else {
+        return Ok(());
+    };
+    a007_marketplace_product::dimensions::upsert(
+        product_id,
+        dimensions.length.map(f64::from),
+        dimensions.width.map(f64::from),
+        dimensions.height.map(f64::from),
+    )
+    .await
+}
@@ -1,6 +1,8 @@
 use super::super::wildberries_api_client::WbSaleRow;
 use crate::domain::a012_wb_sales;
 use crate::domain::a012_wb_sales::service::PostingPreparationCache;
+use crate::shared::marketplaces::field_mapping::a012_wb_sales::MAPPING;
+use crate::shared::marketplaces::parse::FieldErrors;
 use crate::shared::marketplaces::wildberries::datetime::parse_wb_datetime;
 use anyhow::Result;
 use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
@@ -8,6 +10,7 @@ use contracts::domain::a012_wb_sales::aggregate::{
     WbSales, WbSalesHeader, WbSalesLine, WbSalesSourceMeta, WbSalesState, WbSalesWarehouse,
 };
 use contracts::domain::common::AggregateId;
+use serde_json::Value;
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -17,8 +20,17 @@ use uuid::Uuid;
 /// the current import batch.  Providing it avoids an individual SELECT per row.
 /// Pass an empty map to fall back to per-row DB lookups (legacy behaviour).
 ///
+/// Line, state and warehouse fields are read from `raw_json` through the
+/// declarative `field_mapping::a012_wb_sales` table; keys stay on `sale_row`.
+///
 /// `cache` is shared across the whole batch so that repeated lookups for the
 /// same products / organisations / prices are served from memory.
+///
+/// `session_id` is stored on the document so its import provenance
+/// (`sys_import_batches`) can be looked up from the card.
+///
+/// Values present in the payload but not parseable as their mapped type are
+/// appended to `field_errors` for the import report.
 pub async fn process_sale_row(
     connection: &ConnectionMP,
     organization_id: &str,
@@ -26,19 +38,26 @@ pub async fn process_sale_row(
     raw_json: &str,
     existing_sale_ids: &HashMap<String, Uuid>,
     cache: &mut PostingPreparationCache,
+    session_id: &str,
+    field_errors: &mut FieldErrors,
 ) -> Result<bool> {
+    let raw_value: Value = serde_json::from_str(raw_json)?;
+    let payload = MAPPING.apply(&raw_value);
+    let qty = payload.i64("qty");
+
     // SRID — уникальный идентификатор строки продажи
     let document_no = sale_row
         .srid
         .clone()
         .unwrap_or_else(|| format!("WB_{}", chrono::Utc::now().timestamp()));
+    field_errors.extend(&document_no, payload.parse_errors());
 
     // sale_id — ГЛАВНЫЙ ключ дедупликации
     let sale_id = if let Some(sid) = sale_row.sale_id.clone() {
         sid
     } else {
-        let supplier_article = sale_row.supplier_article.clone().unwrap_or_default();
-        let event_type = if sale_row.quantity.unwrap_or(0) < 0 {
+        let supplier_article = payload.str("supplier_article").unwrap_or_default();
+        let event_type = if qty.unwrap_or(0) < 0 {
             "return"
         } else {
             "sale"
@@ -71,28 +90,25 @@ pub async fn process_sale_row(
         marketplace_id: connection.marketplace_id.clone(),
     };
 
-    let supplier_article = sale_row.supplier_article.clone().unwrap_or_default();
+    let supplier_article = payload.str("supplier_article").unwrap_or_default();
 
     let line = WbSalesLine {
         line_id: sale_row.srid.clone().unwrap_or_else(|| document_no.clone()),
         supplier_article: supplier_article.clone(),
-        nm_id: sale_row.nm_id.unwrap_or(0),
-        barcode: sale_row.barcode.clone().unwrap_or_default(),
-        name: sale_row
-            .brand
-            .clone()
-            .unwrap_or_else(|| "Unknown".to_string()),
-        qty: sale_row.quantity.unwrap_or(1) as f64,
-        price_list: sale_row.price_with_disc,
-        discount_total: sale_row.discount,
-        price_effective: sale_row.price_with_disc,
-        amount_line: sale_row.for_pay,
+        nm_id: payload.i64("nm_id").unwrap_or(0),
+        barcode: payload.str("barcode").unwrap_or_default(),
+        name: payload.str("name").unwrap_or_else(|| "Unknown".to_string()),
+        qty: qty.unwrap_or(1) as f64,
+        price_list: payload.f64("price_list"),
+        discount_total: payload.f64("discount_total"),
+        price_effective: payload.f64("price_effective"),
+        amount_line: payload.f64("amount_line"),
         currency_code: Some("RUB".to_string()),
-        total_price: sale_row.total_price,
-        payment_sale_amount: sale_row.payment_sale_amount,
-        discount_percent: sale_row.discount_percent,
-        spp: sale_row.spp,
-        finished_price: sale_row.finished_price,
+        total_price: payload.f64("total_price"),
+        payment_sale_amount: payload.f64("payment_sale_amount"),
+        discount_percent: payload.f64("discount_percent"),
+        spp: payload.f64("spp"),
+        finished_price: payload.f64("finished_price"),
         is_fact: None,
         sell_out_plan: None,
         sell_out_fact: None,
@@ -110,18 +126,17 @@ pub async fn process_sale_row(
         dealer_price_ut: None,
     };
 
-    let sale_dt = if let Some(date_str) = sale_row.sale_dt.as_ref() {
-        parse_wb_datetime(date_str).unwrap_or_else(chrono::Utc::now)
+    let sale_dt = if let Some(date_str) = payload.str("sale_dt") {
+        parse_wb_datetime(&date_str).unwrap_or_else(chrono::Utc::now)
     } else {
         chrono::Utc::now()
     };
 
-    let last_change_dt = sale_row
-        .last_change_date
-        .as_ref()
-        .and_then(|date_str| parse_wb_datetime(date_str));
+    let last_change_dt = payload
+        .str("last_change_dt")
+        .and_then(|date_str| parse_wb_datetime(&date_str));
 
-    let event_type = if sale_row.quantity.unwrap_or(0) < 0 {
+    let event_type = if qty.unwrap_or(0) < 0 {
         "return".to_string()
     } else {
         "sale".to_string()
@@ -136,19 +151,20 @@ pub async fn process_sale_row(
         },
         sale_dt,
         last_change_dt,
-        is_supply: sale_row.is_supply,
-        is_realization: sale_row.is_realization,
+        is_supply: payload.bool("is_supply"),
+        is_realization: payload.bool("is_realization"),
     };
 
     let warehouse = WbSalesWarehouse {
-        warehouse_name: sale_row.warehouse_name.clone(),
-        warehouse_type: sale_row.warehouse_type.clone(),
+        warehouse_name: payload.str("warehouse_name"),
+        warehouse_type: payload.str("warehouse_type"),
     };
 
     let source_meta = WbSalesSourceMeta {
         raw_payload_ref: String::new(),
         fetched_at: chrono::Utc::now(),
-        document_version: 1,
+        document_version: payload.version() as i32,
+        import_session_id: Some(session_id.to_string()),
     };
 
     let mut document = WbSales::new_for_insert(
@@ -159,8 +175,14 @@ pub async fn process_sale_row(
         state,
         warehouse,
         source_meta,
-        true,
+        !connection.is_sandbox,
     );
+    document.is_sandbox = connection.is_sandbox;
+    if let Some(description) =
+        crate::system::description_templates::service::describe_a012(&document).await
+    {
+        document.base.description = description;
+    }
 
     tracing::debug!(
         "Processing WB sale: sale_id={}, document_no={}, event_type={}, supplier_article={}",
@@ -188,6 +188,16 @@ impl ProgressTracker {
         }
     }
 
+    /// Повтор запроса после временного сбоя: считается и как отдельный HTTP-запрос.
+    pub fn record_http_retry(&self, session_id: &str) {
+        let mut sessions = self.sessions.write().unwrap();
+        if let Some(progress) = sessions.get_mut(session_id) {
+            progress.http_retry_count = progress.http_retry_count.saturating_add(1);
+            progress.http_request_count = progress.http_request_count.saturating_add(1);
+            progress.updated_at = chrono::Utc::now();
+        }
+    }
+
     /// Добавляет размер тела ответа к уже учтённой попытке запроса.
     pub fn record_http_response_body(&self, session_id: &str, response_body_len: u64) {
         let mut sessions = self.sessions.write().unwrap();
@@ -199,6 +209,19 @@ impl ProgressTracker {
         }
     }
 
+    /// Итоги дедупликации raw JSON за сессию.
+    pub fn record_raw_payloads(&self, session_id: &str, stored: u64, deduplicated: u64) {
+        let mut sessions = self.sessions.write().unwrap();
+        if let Some(progress) = sessions.get_mut(session_id) {
+            progress.raw_payloads_stored =
+                progress.raw_payloads_stored.saturating_add(stored as i64);
+            progress.raw_payloads_deduplicated = progress
+                .raw_payloads_deduplicated
+                .saturating_add(deduplicated as i64);
+            progress.updated_at = chrono::Utc::now();
+        }
+    }
+
     /// Завершить сессию импорта
     pub fn complete_session(&self, session_id: &str, status: ImportStatus) {
         let mut sessions = self.sessions.write().unwrap();
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::common::AggregateId;
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT};
 use serde::{Deserialize, Serialize};
 use std::fs::OpenOptions;
@@ -7,12 +8,28 @@ use std::io::Write;
 use std::sync::{Arc, Mutex};
 
 use super::progress_tracker::ProgressTracker;
+use crate::shared::marketplaces::retry::{send_with_retry, RetryPolicy};
+use crate::shared::marketplaces::sandbox;
 use crate::shared::marketplaces::wildberries::datetime::{
     format_wb_cursor_datetime, parse_wb_datetime, wb_day_end_utc, wb_day_start_utc,
 };
+use crate::shared::marketplaces::{field_mapping, schema_guard};
+use crate::system::import_provenance;
 
 const WB_ORDERS_MAX_RATE_LIMIT_SLEEP_SECS: u64 = 300;
 
+/// Эндпоинты в журнале расхождений схемы (`sys_schema_drift`).
+const WB_SALES_ENDPOINT: &str = "WB /api/v1/supplier/sales";
+const WB_ORDERS_ENDPOINT: &str = "WB /api/v1/supplier/orders";
+
+/// Сколько раз запрашиваем одну страницу финансового отчёта при таймауте, обрыве связи или 5xx.
+const WB_FINANCE_PAGE_MAX_ATTEMPTS: u32 = 4;
+
+/// Пауза перед повтором: не меньше минуты (лимит 1 запрос/мин), с каждой попыткой дольше.
+fn finance_page_retry_delay_secs(attempt: u32) -> u64 {
+    65 * u64::from(attempt.max(1))
+}
+
 #[derive(Debug, Clone, Default)]
 struct WbRateLimitHeaders {
     retry_seconds: Option<u64>,
@@ -173,6 +190,14 @@ impl WildberriesApiClient {
         }
     }
 
+    fn record_http_retry(&self) {
+        if let Ok(guard) = self.http_track.lock() {
+            if let Some((tracker, sid)) = guard.as_ref() {
+                tracker.record_http_retry(sid);
+            }
+        }
+    }
+
     fn record_http_response_body(&self, response_body_len: u64) {
         if let Ok(guard) = self.http_track.lock() {
             if let Some((tracker, sid)) = guard.as_ref() {
@@ -189,6 +214,36 @@ impl WildberriesApiClient {
         }
     }
 
+    /// Пишет пакет ответа в журнал происхождения импорта (`sys_import_batches`).
+    /// Вне сессии импорта (нет привязки трекера) ничего не делает.
+    async fn record_import_batch(
+        &self,
+        connection: &ConnectionMP,
+        aggregate_index: &str,
+        endpoint: &str,
+        request_params: serde_json::Value,
+        body: &str,
+        row_count: usize,
+    ) {
+        let session_id = match self.http_track.lock() {
+            Ok(guard) => guard.as_ref().map(|(_, sid)| sid.clone()),
+            Err(_) => None,
+        };
+        let Some(session_id) = session_id else {
+            return;
+        };
+        import_provenance::record_batch(import_provenance::ImportBatch {
+            session_id: &session_id,
+            connection_id: &connection.base.id.as_string(),
+            aggregate_index,
+            endpoint,
+            request_params,
+            response_body: body,
+            row_count,
+        })
+        .await;
+    }
+
     async fn read_body_for_recorded_request(
         &self,
         response: reqwest::Response,
@@ -203,6 +258,9 @@ impl WildberriesApiClient {
         &self,
         connection: &ConnectionMP,
     ) -> Result<Vec<DiagnosticResult>> {
+        if connection.is_sandbox {
+            anyhow::bail!("Диагностика недоступна для подключения в режиме песочницы");
+        }
         let mut results = Vec::new();
 
         // Р’Р°СЂРёР°РЅС‚ 1: РўРµРєСѓС‰Р°СЏ СЂРµР°Р»РёР·Р°С†РёСЏ (РїСѓСЃС‚РѕР№ С„РёР»СЊС‚СЂ, limit=100)
@@ -1579,7 +1637,10 @@ impl WildberriesApiClient {
             "https://content-api.wildberries.ru"
         };
 
-        let url = format!("{}/content/v2/get/cards/list", base_url);
+        let url = sandbox::endpoint(
+            connection,
+            &format!("{}/content/v2/get/cards/list", base_url),
+        )?;
 
         if connection.api_key.trim().is_empty() {
             anyhow::bail!("API Key is required for Wildberries API");
@@ -1727,7 +1788,10 @@ impl WildberriesApiClient {
         date_from: chrono::NaiveDate,
         date_to: chrono::NaiveDate,
     ) -> Result<Vec<(WbSaleRow, String)>> {
-        let url = "https://statistics-api.wildberries.ru/api/v1/supplier/sales";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://statistics-api.wildberries.ru/api/v1/supplier/sales",
+        )?;
 
         if connection.api_key.trim().is_empty() {
             anyhow::bail!("API Key is required for Wildberries API");
@@ -1769,17 +1833,32 @@ impl WildberriesApiClient {
             ));
 
             self.record_http_request_attempt(0);
-            let response = match self
-                .client
-                .get(url)
-                .header("Authorization", &connection.api_key)
-                .query(&[
-                    ("dateFrom", date_from_str.as_str()),
-                    ("dateTo", date_to_str.as_str()),
-                    ("flag", &page_flag.to_string()),
-                ])
-                .send()
-                .await
+            let page_flag_str = page_flag.to_string();
+            let response = match send_with_retry(
+                &RetryPolicy::from_config(),
+                "Wildberries Sales API",
+                || {
+                    self.client
+                        .get(url)
+                        .header("Authorization", &connection.api_key)
+                        .query(&[
+                            ("dateFrom", date_from_str.as_str()),
+                            ("dateTo", date_to_str.as_str()),
+                            ("flag", page_flag_str.as_str()),
+                        ])
+                },
+                |event| {
+                    self.record_http_retry();
+                    self.log_to_file(&format!(
+                        "{}: retry {}/{} in {:.1}s",
+                        event.reason,
+                        event.retry,
+                        event.max_attempts - 1,
+                        event.delay.as_secs_f64()
+                    ));
+                },
+            )
+            .await
             {
                 Ok(resp) => resp,
                 Err(e) => {
@@ -1829,16 +1908,29 @@ impl WildberriesApiClient {
                 body_preview
             ));
 
-            match serde_json::from_str::<Vec<WbSaleRow>>(&body) {
-                Ok(page_data) => {
-                    let page_count = page_data.len();
+            match serde_json::from_str::<Vec<serde_json::Value>>(&body) {
+                Ok(page_values) => {
+                    let page_count = page_values.len();
+                    self.record_import_batch(
+                        connection,
+                        "a012_wb_sales",
+                        url,
+                        serde_json::json!({
+                            "dateFrom": date_from_str,
+                            "dateTo": date_to_str,
+                            "flag": page_flag,
+                        }),
+                        &body,
+                        page_count,
+                    )
+                    .await;
                     self.log_to_file(&format!("в”‚ Received: {} records", page_count));
                     self.log_to_file(&format!(
                         "в”‚ Total so far: {} records",
                         all_sales.len() + page_count
                     ));
 
-                    if page_data.is_empty() {
+                    if page_values.is_empty() {
                         self.log_to_file(&format!("в”‚ вњ“ Empty response - all records loaded"));
                         self.log_to_file(&format!(
                             "в””в”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”"
@@ -1846,14 +1938,22 @@ impl WildberriesApiClient {
                         break;
                     }
 
-                    // РџР°СЂСЃРёРј С‚РµР»Рѕ РєР°Рє РјР°СЃСЃРёРІ serde_json::Value РґР»СЏ СЃРѕС…СЂР°РЅРµРЅРёСЏ РѕСЂРёРіРёРЅР°Р»СЊРЅРѕРіРѕ JSON
-                    // Р•СЃР»Рё РЅРµ РїРѕР»СѓС‡Р°РµС‚СЃСЏ вЂ” РёСЃРїРѕР»СЊР·СѓРµРј РїСѓСЃС‚РѕР№ РѕР±СЉРµРєС‚ РєР°Рє fallback
-                    let raw_values: Vec<serde_json::Value> =
-                        serde_json::from_str(&body).unwrap_or_default();
+                    let guarded = schema_guard::guard_rows::<WbSaleRow>(
+                        WB_SALES_ENDPOINT,
+                        &field_mapping::a012_wb_sales::MAPPING,
+                        page_values,
+                    )
+                    .await;
+                    if guarded.rejected > 0 {
+                        self.log_to_file(&format!(
+                            "│ Schema drift: {} rows rejected (see quality check schema_drift)",
+                            guarded.rejected
+                        ));
+                    }
 
-                    let page_pairs: Vec<(WbSaleRow, String)> = page_data
+                    let page_pairs: Vec<(WbSaleRow, String)> = guarded
+                        .rows
                         .into_iter()
-                        .zip(raw_values.into_iter())
                         .map(|(row, raw_val)| {
                             let raw_str = serde_json::to_string(&raw_val)
                                 .unwrap_or_else(|_| "{}".to_string());
@@ -1920,6 +2020,30 @@ impl WildberriesApiClient {
         Ok(all_sales)
     }
 
+    /// Пауза перед повтором страницы финансового отчёта после временной ошибки.
+    async fn wait_finance_page_retry(&self, page_num: u32, rrdid: i64, attempt: u32, reason: &str) {
+        let wait_secs = finance_page_retry_delay_secs(attempt);
+        self.log_to_file(&format!(
+            "│ Page {} (rrdid={}) failed: {}. Retry {}/{} in {} s",
+            page_num,
+            rrdid,
+            reason,
+            attempt,
+            WB_FINANCE_PAGE_MAX_ATTEMPTS - 1,
+            wait_secs
+        ));
+        tracing::warn!(
+            "WB Finance Report page {} (rrdid={}) failed: {}. Retry {}/{} in {}s",
+            page_num,
+            rrdid,
+            reason,
+            attempt,
+            WB_FINANCE_PAGE_MAX_ATTEMPTS - 1,
+            wait_secs
+        );
+        tokio::time::sleep(tokio::time::Duration::from_secs(wait_secs)).await;
+    }
+
     /// Р—Р°РіСЂСѓР·РёС‚СЊ С„РёРЅР°РЅСЃРѕРІС‹Рµ РѕС‚С‡РµС‚С‹ РёР· Wildberries РїРѕ РїРµСЂРёРѕРґСѓ (reportDetailByPeriod)
     /// Р’РѕР·РІСЂР°С‰Р°РµС‚ С‚РѕР»СЊРєРѕ Р•Р–Р•Р”РќР•Р’РќР«Р• РѕС‚С‡РµС‚С‹ (report_type = 1)
     ///
@@ -1931,7 +2055,10 @@ impl WildberriesApiClient {
         date_from: chrono::NaiveDate,
         date_to: chrono::NaiveDate,
     ) -> Result<Vec<WbFinanceReportRow>> {
-        let url = "https://statistics-api.wildberries.ru/api/v5/supplier/reportDetailByPeriod";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://statistics-api.wildberries.ru/api/v5/supplier/reportDetailByPeriod",
+        )?;
 
         if connection.api_key.trim().is_empty() {
             anyhow::bail!("API Key is required for Wildberries API");
@@ -1959,6 +2086,7 @@ impl WildberriesApiClient {
         let mut rrdid: i64 = 0; // РќР°С‡РёРЅР°РµРј СЃ 0 РґР»СЏ РїРµСЂРІРѕР№ СЃС‚СЂР°РЅРёС†С‹
         let limit = 100000; // РњР°РєСЃРёРјР°Р»СЊРЅС‹Р№ Р»РёРјРёС‚ Р·Р°РїРёСЃРµР№
         let mut page_num = 1;
+        let mut page_attempt: u32 = 0;
 
         loop {
             self.log_to_file(&format!(
@@ -1997,6 +2125,15 @@ impl WildberriesApiClient {
                     self.log_to_file(&error_msg);
                     tracing::error!("Wildberries Finance Report API connection error: {}", e);
 
+                    // Таймаут/обрыв связи: повторяем текущую страницу, уже загруженные
+                    // страницы остаются в памяти — загрузка дня не начинается заново
+                    page_attempt += 1;
+                    if page_attempt < WB_FINANCE_PAGE_MAX_ATTEMPTS {
+                        self.wait_finance_page_retry(page_num, rrdid, page_attempt, &e.to_string())
+                            .await;
+                        continue;
+                    }
+
                     // РџСЂРѕРІРµСЂСЏРµРј РєРѕРЅРєСЂРµС‚РЅС‹Рµ С‚РёРїС‹ РѕС€РёР±РѕРє
                     if e.is_timeout() {
                         anyhow::bail!("Request timeout: API РЅРµ РѕС‚РІРµС‚РёР» РІ С‚РµС‡РµРЅРёРµ 60 СЃРµРєСѓРЅРґ");
@@ -2029,6 +2166,14 @@ impl WildberriesApiClient {
                 break;
             }
 
+            // 5xx — временный сбой WB: повторяем ту же страницу (тот же rrdid)
+            if status.is_server_error() && page_attempt + 1 < WB_FINANCE_PAGE_MAX_ATTEMPTS {
+                page_attempt += 1;
+                self.wait_finance_page_retry(page_num, rrdid, page_attempt, &status.to_string())
+                    .await;
+                continue;
+            }
+
             if !status.is_success() {
                 let body = self
                     .read_body_for_recorded_request(response)
@@ -2043,7 +2188,20 @@ impl WildberriesApiClient {
                 );
             }
 
-            let body = self.read_body_tracked(response).await?;
+            let body = match self.read_body_tracked(response).await {
+                Ok(body) => body,
+                Err(e) => {
+                    self.log_to_file(&format!("Failed to read response body: {:?}", e));
+                    page_attempt += 1;
+                    if page_attempt < WB_FINANCE_PAGE_MAX_ATTEMPTS {
+                        self.wait_finance_page_retry(page_num, rrdid, page_attempt, &e.to_string())
+                            .await;
+                        continue;
+                    }
+                    return Err(e.into());
+                }
+            };
+            page_attempt = 0;
 
             // РџСѓСЃС‚РѕР№ РѕС‚РІРµС‚ - РєРѕРЅРµС† РґР°РЅРЅС‹С…
             if body.trim().is_empty() || body.trim() == "[]" {
@@ -2183,7 +2341,10 @@ impl WildberriesApiClient {
         date_from: chrono::NaiveDate,
         date_to: chrono::NaiveDate,
     ) -> Result<Vec<WbOrderRow>> {
-        let url = "https://statistics-api.wildberries.ru/api/v1/supplier/orders";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://statistics-api.wildberries.ru/api/v1/supplier/orders",
+        )?;
 
         if connection.api_key.trim().is_empty() {
             anyhow::bail!("API Key is required for Wildberries API");
@@ -2228,13 +2389,43 @@ impl WildberriesApiClient {
             );
             self.record_http_request_attempt(0);
 
-            let response = match self
-                .client
-                .get(url)
-                .header("Authorization", &connection.api_key)
-                .query(&[("dateFrom", cursor.as_str()), ("flag", "0")])
-                .send()
-                .await
+            let policy = RetryPolicy {
+                max_retry_after: std::time::Duration::from_secs(
+                    WB_ORDERS_MAX_RATE_LIMIT_SLEEP_SECS,
+                ),
+                ..RetryPolicy::from_config()
+            };
+            let response = match send_with_retry(
+                &policy,
+                "Wildberries Orders API",
+                || {
+                    self.client
+                        .get(url)
+                        .header("Authorization", &connection.api_key)
+                        .query(&[("dateFrom", cursor.as_str()), ("flag", "0")])
+                },
+                |event| {
+                    self.record_http_retry();
+                    self.log_to_file(&format!(
+                        "{}: retry {}/{} in {:.1}s",
+                        event.reason,
+                        event.retry,
+                        event.max_attempts - 1,
+                        event.delay.as_secs_f64()
+                    ));
+                    self.set_tracked_current_item(
+                        "a015_wb_orders",
+                        format!(
+                            "WB Orders API: {}, повтор {}/{} через {} сек.",
+                            event.reason,
+                            event.retry,
+                            event.max_attempts - 1,
+                            event.delay.as_secs()
+                        ),
+                    );
+                },
+            )
+            .await
             {
                 Ok(resp) => resp,
                 Err(e) => {
@@ -2408,9 +2599,18 @@ impl WildberriesApiClient {
                 body_preview
             ));
 
-            match serde_json::from_str::<Vec<WbOrderRow>>(&body) {
-                Ok(page_data) => {
-                    let page_count = page_data.len();
+            match serde_json::from_str::<Vec<serde_json::Value>>(&body) {
+                Ok(page_values) => {
+                    let page_count = page_values.len();
+                    self.record_import_batch(
+                        connection,
+                        "a015_wb_orders",
+                        url,
+                        serde_json::json!({ "dateFrom": cursor, "flag": 0 }),
+                        &body,
+                        page_count,
+                    )
+                    .await;
                     self.log_to_file(&format!(
                         "в”‚ Received: {} rows on page {}",
                         page_count, page_num
@@ -2423,9 +2623,22 @@ impl WildberriesApiClient {
                         "в””в”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”Ђв”"
                     ));
 
+                    let guarded = schema_guard::guard_rows::<WbOrderRow>(
+                        WB_ORDERS_ENDPOINT,
+                        &field_mapping::a015_wb_orders::MAPPING,
+                        page_values,
+                    )
+                    .await;
+                    if guarded.rejected > 0 {
+                        self.log_to_file(&format!(
+                            "│ Schema drift: {} rows rejected (see quality check schema_drift)",
+                            guarded.rejected
+                        ));
+                    }
+
                     let mut max_last_change = None::<chrono::DateTime<chrono::Utc>>;
                     let mut kept_rows = 0usize;
-                    for row in page_data {
+                    for (row, _) in guarded.rows {
                         let row_last_change =
                             row.last_change_date.as_deref().and_then(parse_wb_datetime);
 
@@ -2518,7 +2731,10 @@ impl WildberriesApiClient {
         // немедленно возвращаем ошибку, чтобы не ждать часами.
         const QUOTA_EXHAUSTED_THRESHOLD_SECS: u64 = 300; // 5 минут
 
-        let url = "https://documents-api.wildberries.ru/api/v1/documents/list";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://documents-api.wildberries.ru/api/v1/documents/list",
+        )?;
 
         if connection.api_key.trim().is_empty() {
             anyhow::bail!("API Key is required for Wildberries API");
@@ -2660,7 +2876,10 @@ impl WildberriesApiClient {
         service_name: &str,
         extension: &str,
     ) -> Result<WbDocumentDownloadFile> {
-        let url = "https://documents-api.wildberries.ru/api/v1/documents/download";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://documents-api.wildberries.ru/api/v1/documents/download",
+        )?;
 
         if connection.api_key.trim().is_empty() {
             anyhow::bail!("API Key is required for Wildberries API");
@@ -2700,7 +2919,10 @@ impl WildberriesApiClient {
         &self,
         connection: &ConnectionMP,
     ) -> Result<Vec<CommissionTariffRow>> {
-        let url = "https://common-api.wildberries.ru/api/v1/tariffs/commission?locale=ru";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://common-api.wildberries.ru/api/v1/tariffs/commission?locale=ru",
+        )?;
 
         if connection.api_key.trim().is_empty() {
             anyhow::bail!("API Key is required for Wildberries Commission Tariffs API");
@@ -2794,10 +3016,10 @@ impl WildberriesApiClient {
         limit: i32,
         offset: i32,
     ) -> Result<Vec<WbGoodsPriceRow>> {
-        let url = format!(
+        let url = sandbox::endpoint(connection, &format!(
             "https://discounts-prices-api.wildberries.ru/api/v2/list/goods/filter?limit={}&offset={}",
             limit, offset
-        );
+        ))?;
 
         if connection.api_key.trim().is_empty() {
             anyhow::bail!("API Key is required for Wildberries Prices API");
@@ -2874,10 +3096,10 @@ impl WildberriesApiClient {
         end_date_time: &str,
         all_promo: bool,
     ) -> Result<Vec<WbCalendarPromotion>> {
-        let url = format!(
+        let url = sandbox::endpoint(connection, &format!(
             "https://dp-calendar-api.wildberries.ru/api/v1/calendar/promotions?startDateTime={}&endDateTime={}&allPromo={}",
             start_date_time, end_date_time, all_promo
-        );
+        ))?;
 
         if connection.api_key.trim().is_empty() {
             anyhow::bail!("API Key is required for Wildberries Promotion API");
@@ -2977,10 +3199,13 @@ impl WildberriesApiClient {
             .collect::<Vec<_>>()
             .join("&");
 
-        let url = format!(
-            "https://dp-calendar-api.wildberries.ru/api/v1/calendar/promotions/details?{}",
-            query
-        );
+        let url = sandbox::endpoint(
+            connection,
+            &format!(
+                "https://dp-calendar-api.wildberries.ru/api/v1/calendar/promotions/details?{}",
+                query
+            ),
+        )?;
 
         self.log_to_file(&format!(
             "=== REQUEST ===\nGET {}\nAuthorization: ****",
@@ -3060,10 +3285,10 @@ impl WildberriesApiClient {
         for in_action in [true, false] {
             let mut offset: u32 = 0;
             loop {
-                let url = format!(
+                let url = sandbox::endpoint(connection, &format!(
                     "https://dp-calendar-api.wildberries.ru/api/v1/calendar/promotions/nomenclatures?promotionID={}&inAction={}&limit={}&offset={}",
                     promotion_id, in_action, page_size, offset
-                );
+                ))?;
 
                 self.log_to_file(&format!(
                     "=== REQUEST ===\nGET {}\nAuthorization: ****",
@@ -3143,7 +3368,10 @@ impl WildberriesApiClient {
 
     /// GET /adv/v1/promotion/count вЂ” РїРѕР»СѓС‡РёС‚СЊ РІСЃРµ advertId СЂРµРєР»Р°РјРЅС‹С… РєР°РјРїР°РЅРёР№ (СЃС‚Р°С‚СѓСЃС‹ 7, 9, 11)
     pub async fn fetch_advert_campaign_ids(&self, connection: &ConnectionMP) -> Result<Vec<i64>> {
-        let url = "https://advert-api.wildberries.ru/adv/v1/promotion/count";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://advert-api.wildberries.ru/adv/v1/promotion/count",
+        )?;
 
         if connection.api_key.trim().is_empty() {
             anyhow::bail!("API Key is required for Wildberries Advert API");
@@ -3234,7 +3462,10 @@ impl WildberriesApiClient {
         &self,
         connection: &ConnectionMP,
     ) -> Result<Vec<WbAdvertCampaignSummary>> {
-        let url = "https://advert-api.wildberries.ru/adv/v1/promotion/count";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://advert-api.wildberries.ru/adv/v1/promotion/count",
+        )?;
 
         if connection.api_key.trim().is_empty() {
             anyhow::bail!("API Key is required for Wildberries Advert API");
@@ -3308,10 +3539,13 @@ impl WildberriesApiClient {
             .map(|id| id.to_string())
             .collect::<Vec<_>>()
             .join(",");
-        let url = format!(
-            "https://advert-api.wildberries.ru/api/advert/v2/adverts?ids={}",
-            ids_str
-        );
+        let url = sandbox::endpoint(
+            connection,
+            &format!(
+                "https://advert-api.wildberries.ru/api/advert/v2/adverts?ids={}",
+                ids_str
+            ),
+        )?;
 
         let response = self
             .client
@@ -3370,10 +3604,13 @@ impl WildberriesApiClient {
             .map(|id| id.to_string())
             .collect::<Vec<_>>()
             .join(",");
-        let url = format!(
-            "https://advert-api.wildberries.ru/api/advert/v2/adverts?ids={}",
-            ids_str
-        );
+        let url = sandbox::endpoint(
+            connection,
+            &format!(
+                "https://advert-api.wildberries.ru/api/advert/v2/adverts?ids={}",
+                ids_str
+            ),
+        )?;
 
         let response = self
             .client
@@ -3436,10 +3673,13 @@ impl WildberriesApiClient {
             .collect::<Vec<_>>()
             .join(",");
 
-        let url = format!(
-            "https://advert-api.wildberries.ru/adv/v3/fullstats?ids={}&beginDate={}&endDate={}",
-            ids_str, begin_date, end_date
-        );
+        let url = sandbox::endpoint(
+            connection,
+            &format!(
+                "https://advert-api.wildberries.ru/adv/v3/fullstats?ids={}&beginDate={}&endDate={}",
+                ids_str, begin_date, end_date
+            ),
+        )?;
 
         self.log_to_file(&format!(
             "=== REQUEST ===\nGET {}\nAuthorization: ****",
@@ -3536,8 +3776,10 @@ impl WildberriesApiClient {
         offset: usize,
         limit: usize,
     ) -> Result<(Vec<i64>, bool)> {
-        let url =
-            "https://seller-analytics-api.wildberries.ru/api/analytics/v3/sales-funnel/products";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://seller-analytics-api.wildberries.ru/api/analytics/v3/sales-funnel/products",
+        )?;
 
         let request_body = serde_json::json!({
             "selectedPeriod": { "start": date_from, "end": date_to },
@@ -3668,8 +3910,10 @@ impl WildberriesApiClient {
         offset: usize,
         limit: usize,
     ) -> Result<(Vec<WbProductSnapshotRow>, bool)> {
-        let url =
-            "https://seller-analytics-api.wildberries.ru/api/analytics/v3/sales-funnel/products";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://seller-analytics-api.wildberries.ru/api/analytics/v3/sales-funnel/products",
+        )?;
 
         let request_body = serde_json::json!({
             "selectedPeriod": { "start": date_from, "end": date_to },
@@ -3844,7 +4088,7 @@ impl WildberriesApiClient {
             return Ok(vec![]);
         }
 
-        let url = "https://seller-analytics-api.wildberries.ru/api/analytics/v3/sales-funnel/products/history";
+        let url = &sandbox::endpoint(connection, "https://seller-analytics-api.wildberries.ru/api/analytics/v3/sales-funnel/products/history")?;
 
         let request_body = serde_json::json!({
             "selectedPeriod": { "start": date_from, "end": date_to },
@@ -3978,7 +4222,10 @@ impl WildberriesApiClient {
     ) -> Result<Vec<WbSearchReportRow>> {
         // The main `/report` method only returns summary widgets and groups.
         // Product rows are provided by `/table/details`, including when no group filter is set.
-        let url = "https://seller-analytics-api.wildberries.ru/api/v2/search-report/table/details";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://seller-analytics-api.wildberries.ru/api/v2/search-report/table/details",
+        )?;
         // pastPeriod обязателен: окно той же длины, оканчивающееся за день до currentPeriod.
         let (past_start, past_end) = past_period(date_from, date_to);
         let request_body = serde_json::json!({
@@ -4063,8 +4310,10 @@ impl WildberriesApiClient {
         if nm_ids.is_empty() {
             return Ok(Vec::new());
         }
-        let url =
-            "https://seller-analytics-api.wildberries.ru/api/v2/search-report/product/search-texts";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://seller-analytics-api.wildberries.ru/api/v2/search-report/product/search-texts",
+        )?;
         let (past_start, past_end) = past_period(date_from, date_to);
         let request_body = serde_json::json!({
             "currentPeriod": { "start": date_from, "end": date_to },
@@ -4725,6 +4974,10 @@ pub struct WbOrderRow {
     /// SRID - СѓРЅРёРєР°Р»СЊРЅС‹Р№ РёРґРµРЅС‚РёС„РёРєР°С‚РѕСЂ Р·Р°РєР°Р·Р°
     #[serde(default)]
     pub srid: Option<String>,
+    /// Поля, не описанные в структуре: сохраняются в raw JSON и доступны
+    /// таблице `field_mapping::a015_wb_orders` без правки клиента
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -5307,7 +5560,10 @@ impl WildberriesApiClient {
         date_from: chrono::NaiveDate,
         date_to: chrono::NaiveDate,
     ) -> anyhow::Result<Vec<WbSupplyRow>> {
-        let url = "https://marketplace-api.wildberries.ru/api/v3/supplies";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://marketplace-api.wildberries.ru/api/v3/supplies",
+        )?;
         let mut all_supplies: Vec<WbSupplyRow> = Vec::new();
         let mut next_cursor: i64 = 0;
         let range_start =
@@ -5383,10 +5639,13 @@ impl WildberriesApiClient {
         connection: &contracts::domain::a006_connection_mp::aggregate::ConnectionMP,
         supply_id: &str,
     ) -> anyhow::Result<Vec<i64>> {
-        let url = format!(
-            "https://marketplace-api.wildberries.ru/api/marketplace/v3/supplies/{}/order-ids",
-            supply_id
-        );
+        let url = sandbox::endpoint(
+            connection,
+            &format!(
+                "https://marketplace-api.wildberries.ru/api/marketplace/v3/supplies/{}/order-ids",
+                supply_id
+            ),
+        )?;
 
         let response = self
             .client
@@ -5444,10 +5703,13 @@ impl WildberriesApiClient {
         connection: &contracts::domain::a006_connection_mp::aggregate::ConnectionMP,
         supply_id: &str,
     ) -> anyhow::Result<Vec<i64>> {
-        let url = format!(
-            "https://marketplace-api.wildberries.ru/api/marketplace/v3/supplies/{}/order-ids",
-            supply_id
-        );
+        let url = sandbox::endpoint(
+            connection,
+            &format!(
+                "https://marketplace-api.wildberries.ru/api/marketplace/v3/supplies/{}/order-ids",
+                supply_id
+            ),
+        )?;
 
         let response = self
             .client
@@ -5516,7 +5778,10 @@ impl WildberriesApiClient {
 
         // WB API limit: max 100 order IDs per request
         const BATCH_SIZE: usize = 100;
-        let url = "https://marketplace-api.wildberries.ru/api/v3/orders/stickers";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://marketplace-api.wildberries.ru/api/v3/orders/stickers",
+        )?;
         let mut all_stickers: Vec<WbStickerRow> = Vec::new();
 
         for chunk in order_ids.chunks(BATCH_SIZE) {
@@ -5584,7 +5849,10 @@ impl WildberriesApiClient {
         &self,
         connection: &contracts::domain::a006_connection_mp::aggregate::ConnectionMP,
     ) -> anyhow::Result<Vec<WbMarketplaceOrderRow>> {
-        let url = "https://marketplace-api.wildberries.ru/api/v3/orders/new";
+        let url = &sandbox::endpoint(
+            connection,
+            "https://marketplace-api.wildberries.ru/api/v3/orders/new",
+        )?;
         self.record_http_request_attempt(0);
 
         let response = self
@@ -5642,7 +5910,10 @@ impl WildberriesApiClient {
         let limit = 1000i64;
 
         loop {
-            let url = "https://marketplace-api.wildberries.ru/api/v3/orders";
+            let url = &sandbox::endpoint(
+                connection,
+                "https://marketplace-api.wildberries.ru/api/v3/orders",
+            )?;
             self.record_http_request_attempt(0);
             let response = self
                 .client
@@ -5718,6 +5989,7 @@ impl WildberriesApiClient {
     pub async fn fetch_claims(&self, connection: &ConnectionMP) -> Result<Vec<WbClaimRow>> {
         const BASE_URL: &str = "https://returns-api.wildberries.ru/api/v1/claims";
         const PAGE_LIMIT: u32 = 200;
+        let base_url = sandbox::endpoint(connection, BASE_URL)?;
 
         if connection.api_key.trim().is_empty() {
             anyhow::bail!("API Key is required for WB Buyers Returns API");
@@ -5739,7 +6011,7 @@ impl WildberriesApiClient {
 
                 let resp = match self
                     .client
-                    .get(BASE_URL)
+                    .get(&base_url)
                     .header("Authorization", connection.api_key.trim())
                     .query(&[
                         ("is_archive", is_archive.to_string()),
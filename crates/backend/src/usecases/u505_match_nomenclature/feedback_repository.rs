@@ -0,0 +1,188 @@
+//! Журнал решений по предложениям сопоставления (`u505_match_feedback`).
+//!
+//! Одна строка на пару «товар — номенклатура»; повторное решение заменяет прежнее.
+
+use anyhow::Result;
+use contracts::usecases::u505_match_nomenclature::suggestions::{
+    MatchAccuracyPoint, MatchAccuracyScale,
+};
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ConnectionTrait, EntityTrait, QueryFilter, QuerySelect, Set, Statement};
+use std::collections::{HashMap, HashSet};
+
+use crate::shared::data::db::get_connection;
+
+pub const DECISION_ACCEPTED: &str = "accepted";
+pub const DECISION_REJECTED: &str = "rejected";
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "u505_match_feedback")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub marketplace_product_ref: String,
+    pub nomenclature_ref: String,
+    pub decision: String,
+    pub rule: String,
+    pub brand: String,
+    pub score: f64,
+    #[sea_orm(nullable)]
+    pub decided_by: Option<String>,
+    pub decided_at: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Счётчики решений по одному разрезу.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecisionCounts {
+    pub accepted: i64,
+    pub rejected: i64,
+}
+
+pub fn feedback_id(marketplace_product_ref: &str, nomenclature_ref: &str) -> String {
+    format!("{}:{}", marketplace_product_ref, nomenclature_ref)
+}
+
+pub async fn upsert(model: Model) -> Result<()> {
+    Entity::insert(ActiveModel {
+        id: Set(model.id),
+        marketplace_product_ref: Set(model.marketplace_product_ref),
+        nomenclature_ref: Set(model.nomenclature_ref),
+        decision: Set(model.decision),
+        rule: Set(model.rule),
+        brand: Set(model.brand),
+        score: Set(model.score),
+        decided_by: Set(model.decided_by),
+        decided_at: Set(model.decided_at),
+    })
+    .on_conflict(
+        OnConflict::column(Column::Id)
+            .update_columns([
+                Column::Decision,
+                Column::Rule,
+                Column::Brand,
+                Column::Score,
+                Column::DecidedBy,
+                Column::DecidedAt,
+            ])
+            .to_owned(),
+    )
+    .exec(get_connection())
+    .await?;
+    Ok(())
+}
+
+/// Отклонённые пары (marketplace_product_ref, nomenclature_ref) — больше не предлагаются.
+pub async fn rejected_pairs() -> Result<HashSet<(String, String)>> {
+    let rows: Vec<(String, String)> = Entity::find()
+        .select_only()
+        .column(Column::MarketplaceProductRef)
+        .column(Column::NomenclatureRef)
+        .filter(Column::Decision.eq(DECISION_REJECTED))
+        .into_tuple()
+        .all(get_connection())
+        .await?;
+    Ok(rows.into_iter().collect())
+}
+
+/// Принятые / отклонённые по (brand, rule) — признаки для оценки кандидатов.
+pub async fn counts_by_brand_and_rule() -> Result<HashMap<(String, String), DecisionCounts>> {
+    let db = get_connection();
+    let rows = db
+        .query_all(Statement::from_string(
+            db.get_database_backend(),
+            "SELECT brand, rule, \
+                    SUM(CASE WHEN decision = 'accepted' THEN 1 ELSE 0 END) AS accepted, \
+                    SUM(CASE WHEN decision = 'rejected' THEN 1 ELSE 0 END) AS rejected \
+             FROM u505_match_feedback \
+             GROUP BY brand, rule"
+                .to_string(),
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let brand = row.try_get::<String>("", "brand").ok()?;
+            let rule = row.try_get::<String>("", "rule").ok()?;
+            let counts = DecisionCounts {
+                accepted: row.try_get::<i64>("", "accepted").ok()?,
+                rejected: row.try_get::<i64>("", "rejected").ok()?,
+            };
+            Some(((brand, rule), counts))
+        })
+        .collect())
+}
+
+fn period_expr(scale: MatchAccuracyScale) -> &'static str {
+    match scale {
+        MatchAccuracyScale::Day => "date(decided_at)",
+        // Неделя с понедельника: ближайшее воскресенье минус 6 дней.
+        MatchAccuracyScale::Week => "date(decided_at, 'weekday 0', '-6 days')",
+        MatchAccuracyScale::Month => "date(decided_at, 'start of month')",
+    }
+}
+
+async fn accuracy_grouped(
+    key_expr: &str,
+    date_from: Option<&str>,
+) -> Result<Vec<MatchAccuracyPoint>> {
+    let db = get_connection();
+    let mut values: Vec<sea_orm::Value> = Vec::new();
+    let where_sql = match date_from {
+        Some(date_from) => {
+            values.push(date_from.into());
+            "WHERE date(decided_at) >= ?"
+        }
+        None => "",
+    };
+    let sql = format!(
+        "SELECT {key_expr} AS period_key, \
+                SUM(CASE WHEN decision = 'accepted' THEN 1 ELSE 0 END) AS accepted, \
+                SUM(CASE WHEN decision = 'rejected' THEN 1 ELSE 0 END) AS rejected \
+         FROM u505_match_feedback {where_sql} \
+         GROUP BY period_key ORDER BY period_key"
+    );
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            sql,
+            values,
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let accepted = row.try_get::<i64>("", "accepted").ok()?;
+            let rejected = row.try_get::<i64>("", "rejected").ok()?;
+            Some(MatchAccuracyPoint {
+                key: row.try_get::<String>("", "period_key").ok()?,
+                accepted,
+                rejected,
+                precision: precision(accepted, rejected),
+            })
+        })
+        .collect())
+}
+
+fn precision(accepted: i64, rejected: i64) -> Option<f64> {
+    let total = accepted + rejected;
+    (total > 0).then(|| accepted as f64 / total as f64)
+}
+
+/// Точность по периодам выбранной шкалы.
+pub async fn accuracy_by_period(
+    scale: MatchAccuracyScale,
+    date_from: Option<&str>,
+) -> Result<Vec<MatchAccuracyPoint>> {
+    accuracy_grouped(period_expr(scale), date_from).await
+}
+
+/// Точность по правилам сопоставления.
+pub async fn accuracy_by_rule(date_from: Option<&str>) -> Result<Vec<MatchAccuracyPoint>> {
+    accuracy_grouped("rule", date_from).await
+}
@@ -1,5 +1,7 @@
 pub mod executor;
+pub mod feedback_repository;
 pub mod progress_tracker;
+pub mod suggestions;
 
 pub use executor::MatchExecutor;
 pub use progress_tracker::ProgressTracker;
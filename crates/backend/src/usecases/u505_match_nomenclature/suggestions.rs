@@ -0,0 +1,703 @@
+//! Предложения сопоставления товаров МП с номенклатурой и обучение на решениях.
+//!
+//! Пакетное сопоставление (`MatchExecutor`) связывает только однозначные точные
+//! совпадения артикулов. Для оставшихся товаров без связи здесь строятся кандидаты
+//! по правилам [`MatchRule`]: общий штрихкод (p901), артикул (точно, без
+//! разделителей, по началу) и похожее наименование. Пользователь принимает или
+//! отклоняет кандидатов пачкой; уверенные предложения можно принять все сразу.
+//! Решения пишутся в `u505_match_feedback` и работают как признаки:
+//! - отклонённая пара больше не предлагается;
+//! - оценка кандидата — сглаженная доля принятых решений по (бренд, правило), поэтому
+//!   у бренда, где артикул МП = артикул 1С + размер, правило `prefix` со временем
+//!   поднимается, а у бренда с «шумными» префиксами — опускается ниже порога.
+
+use anyhow::Result;
+use chrono::{SecondsFormat, Utc};
+use contracts::domain::a004_nomenclature::aggregate::Nomenclature;
+use contracts::domain::a007_marketplace_product::aggregate::MarketplaceProduct;
+use contracts::domain::common::AggregateId;
+use contracts::usecases::u505_match_nomenclature::suggestions::{
+    MatchAccuracyQuery, MatchAccuracyReport, MatchAutoAcceptRequest, MatchCandidateDto,
+    MatchDecisionDto, MatchDecisionRequest, MatchDecisionResponse, MatchRule, MatchSuggestionDto,
+    MatchSuggestionListResponse, MatchSuggestionQuery, HIGH_CONFIDENCE_SCORE,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use uuid::Uuid;
+
+use super::feedback_repository::{self, DecisionCounts, DECISION_ACCEPTED, DECISION_REJECTED};
+use crate::domain::{a004_nomenclature, a007_marketplace_product};
+use crate::projections::p901_nomenclature_barcodes;
+
+/// Минимальная длина нормализованного артикула для правила `prefix`:
+/// короче — слишком много случайных совпадений.
+const MIN_PREFIX_LEN: usize = 4;
+
+/// Минимальная схожесть наименований для правила `name`.
+const MIN_NAME_SIMILARITY: f64 = 0.5;
+
+/// Слова, которые встречаются в наименованиях большего числа позиций («футболка»,
+/// «чёрный»), не используются для поиска кандидатов по имени — только для схожести.
+const MAX_TOKEN_POSTINGS: usize = 300;
+
+/// Сколько позиций с наибольшим числом общих слов проверяется на схожесть имени.
+const NAME_LOOKUP_LIMIT: usize = 20;
+
+/// Вес априорной оценки правила в сглаживании: столько «виртуальных» решений
+/// с долей принятия `base_score` добавляется к реальным.
+const PRIOR_WEIGHT: f64 = 4.0;
+
+/// Кандидатов на один товар.
+const MAX_CANDIDATES: usize = 5;
+
+const DEFAULT_MIN_SCORE: f64 = 0.3;
+
+/// Артикул без регистра и разделителей: «AB-12 34» → «ab1234».
+pub fn normalize_article(article: &str) -> String {
+    article
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Слова наименования в нижнем регистре; однобуквенные отбрасываются.
+pub fn name_tokens(name: &str) -> BTreeSet<String> {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| token.chars().count() >= 2)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Схожесть наименований — доля общих слов (коэффициент Жаккара).
+pub fn name_similarity(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let common = a.intersection(b).count();
+    common as f64 / (a.len() + b.len() - common) as f64
+}
+
+fn brand_key(brand: Option<&str>) -> String {
+    brand.map(|b| b.trim().to_lowercase()).unwrap_or_default()
+}
+
+/// Правило по артикулам; `None` — по артикулу не кандидат.
+pub fn match_rule(product_article: &str, nomenclature_article: &str) -> Option<MatchRule> {
+    let product = product_article.trim();
+    let nomenclature = nomenclature_article.trim();
+    if product.is_empty() || nomenclature.is_empty() {
+        return None;
+    }
+    if product.to_lowercase() == nomenclature.to_lowercase() {
+        return Some(MatchRule::Exact);
+    }
+    let product = normalize_article(product);
+    let nomenclature = normalize_article(nomenclature);
+    if product.is_empty() || nomenclature.is_empty() {
+        return None;
+    }
+    if product == nomenclature {
+        return Some(MatchRule::Normalized);
+    }
+    let (shorter, longer) = if product.len() < nomenclature.len() {
+        (&product, &nomenclature)
+    } else {
+        (&nomenclature, &product)
+    };
+    (shorter.chars().count() >= MIN_PREFIX_LEN && longer.starts_with(shorter.as_str()))
+        .then_some(MatchRule::Prefix)
+}
+
+/// Признаки одной стороны пары: артикул, штрихкоды, слова наименования.
+#[derive(Debug, Clone, Default)]
+struct MatchKey {
+    article: String,
+    barcodes: Vec<String>,
+    tokens: BTreeSet<String>,
+}
+
+impl MatchKey {
+    fn product(product: &MarketplaceProduct) -> Self {
+        Self {
+            article: product.article.clone(),
+            barcodes: product
+                .barcode
+                .iter()
+                .map(|b| b.trim().to_string())
+                .filter(|b| !b.is_empty())
+                .collect(),
+            tokens: name_tokens(&product.base.description),
+        }
+    }
+
+    fn nomenclature(nomenclature: &Nomenclature, barcodes: Vec<String>) -> Self {
+        Self {
+            article: nomenclature.article.clone(),
+            barcodes,
+            tokens: name_tokens(&nomenclature.base.description),
+        }
+    }
+}
+
+/// Самое сильное правило для пары и множитель оценки (схожесть имени для `name`,
+/// иначе 1). `None` — пара не кандидат.
+fn classify(product: &MatchKey, nomenclature: &MatchKey) -> Option<(MatchRule, f64)> {
+    if product
+        .barcodes
+        .iter()
+        .any(|barcode| nomenclature.barcodes.contains(barcode))
+    {
+        return Some((MatchRule::Barcode, 1.0));
+    }
+    if let Some(rule) = match_rule(&product.article, &nomenclature.article) {
+        return Some((rule, 1.0));
+    }
+    let similarity = name_similarity(&product.tokens, &nomenclature.tokens);
+    (similarity >= MIN_NAME_SIMILARITY).then_some((MatchRule::Name, similarity))
+}
+
+/// Оценка кандидата: доля принятых решений по (бренд, правило), сглаженная
+/// к базовой оценке правила. Без решений — ровно `base_score`.
+pub fn learned_score(rule: MatchRule, counts: Option<DecisionCounts>) -> f64 {
+    let counts = counts.unwrap_or_default();
+    (counts.accepted as f64 + rule.base_score() * PRIOR_WEIGHT)
+        / ((counts.accepted + counts.rejected) as f64 + PRIOR_WEIGHT)
+}
+
+fn candidate_score(
+    rule: MatchRule,
+    factor: f64,
+    brand: &str,
+    stats: &HashMap<(String, String), DecisionCounts>,
+) -> f64 {
+    let counts = stats
+        .get(&(brand.to_string(), rule.code().to_string()))
+        .copied();
+    learned_score(rule, counts) * factor
+}
+
+/// Индекс номенклатуры для поиска кандидатов: по нормализованному артикулу
+/// (`BTreeMap` — чтобы находить артикулы, для которых артикул товара является
+/// началом), по штрихкоду и по словам наименования.
+struct NomenclatureIndex {
+    items: Vec<Nomenclature>,
+    keys: Vec<MatchKey>,
+    by_article: BTreeMap<String, Vec<usize>>,
+    by_barcode: HashMap<String, Vec<usize>>,
+    by_token: HashMap<String, Vec<usize>>,
+}
+
+impl NomenclatureIndex {
+    fn new(
+        items: Vec<Nomenclature>,
+        barcodes: Vec<p901_nomenclature_barcodes::repository::Model>,
+    ) -> Self {
+        let items: Vec<_> = items
+            .into_iter()
+            .filter(|n| !n.is_folder && !n.base.metadata.is_deleted)
+            .collect();
+        let by_id: HashMap<String, usize> = items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| (item.base.id.as_string(), idx))
+            .collect();
+
+        let mut item_barcodes: Vec<Vec<String>> = vec![Vec::new(); items.len()];
+        let mut by_barcode: HashMap<String, Vec<usize>> = HashMap::new();
+        for row in barcodes.into_iter().filter(|row| row.is_active) {
+            let barcode = row.barcode.trim().to_string();
+            let Some(&idx) = row.nomenclature_ref.as_ref().and_then(|r| by_id.get(r)) else {
+                continue;
+            };
+            if barcode.is_empty() || item_barcodes[idx].contains(&barcode) {
+                continue;
+            }
+            item_barcodes[idx].push(barcode.clone());
+            by_barcode.entry(barcode).or_default().push(idx);
+        }
+
+        let keys: Vec<MatchKey> = items
+            .iter()
+            .zip(item_barcodes)
+            .map(|(item, barcodes)| MatchKey::nomenclature(item, barcodes))
+            .collect();
+
+        let mut by_article: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        let mut by_token: HashMap<String, Vec<usize>> = HashMap::new();
+        for (idx, key) in keys.iter().enumerate() {
+            let article = normalize_article(&key.article);
+            if !article.is_empty() {
+                by_article.entry(article).or_default().push(idx);
+            }
+            for token in &key.tokens {
+                by_token.entry(token.clone()).or_default().push(idx);
+            }
+        }
+        Self {
+            items,
+            keys,
+            by_article,
+            by_barcode,
+            by_token,
+        }
+    }
+
+    /// Позиции, которые могут подойти товару хотя бы по одному правилу.
+    fn lookup(&self, product: &MatchKey) -> BTreeSet<usize> {
+        let mut found: BTreeSet<usize> = BTreeSet::new();
+        for barcode in &product.barcodes {
+            if let Some(indexes) = self.by_barcode.get(barcode) {
+                found.extend(indexes);
+            }
+        }
+        self.lookup_article(&product.article, &mut found);
+        found.extend(self.lookup_name(&product.tokens));
+        found
+    }
+
+    /// Нормализованный артикул номенклатуры совпадает с артикулом товара,
+    /// является его началом или начинается с него.
+    fn lookup_article(&self, product_article: &str, found: &mut BTreeSet<usize>) {
+        let key = normalize_article(product_article);
+        if key.is_empty() {
+            return;
+        }
+        // Артикул номенклатуры — начало артикула товара (и точное совпадение).
+        let chars: Vec<(usize, char)> = key.char_indices().collect();
+        for (count, (offset, ch)) in chars.iter().enumerate() {
+            if count + 1 < MIN_PREFIX_LEN && count + 1 < chars.len() {
+                continue;
+            }
+            let prefix = &key[..offset + ch.len_utf8()];
+            if let Some(indexes) = self.by_article.get(prefix) {
+                found.extend(indexes);
+            }
+        }
+        // Артикул товара — начало артикула номенклатуры.
+        if chars.len() >= MIN_PREFIX_LEN {
+            for (candidate, indexes) in self.by_article.range(key.clone()..) {
+                if !candidate.starts_with(key.as_str()) {
+                    break;
+                }
+                found.extend(indexes);
+            }
+        }
+    }
+
+    /// Позиции с наибольшим числом общих редких слов наименования.
+    fn lookup_name(&self, tokens: &BTreeSet<String>) -> Vec<usize> {
+        let mut shared: HashMap<usize, usize> = HashMap::new();
+        for token in tokens {
+            let Some(indexes) = self.by_token.get(token) else {
+                continue;
+            };
+            if indexes.len() > MAX_TOKEN_POSTINGS {
+                continue;
+            }
+            for &idx in indexes {
+                *shared.entry(idx).or_default() += 1;
+            }
+        }
+        let mut ranked: Vec<(usize, usize)> = shared.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked
+            .into_iter()
+            .take(NAME_LOOKUP_LIMIT)
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
+fn candidates_for(
+    product: &MarketplaceProduct,
+    index: &NomenclatureIndex,
+    stats: &HashMap<(String, String), DecisionCounts>,
+    rejected: &HashSet<(String, String)>,
+    min_score: f64,
+) -> Vec<MatchCandidateDto> {
+    let product_ref = product.base.id.as_string();
+    let product_key = MatchKey::product(product);
+    let brand = brand_key(product.brand.as_deref());
+    let mut candidates: Vec<MatchCandidateDto> = index
+        .lookup(&product_key)
+        .into_iter()
+        .filter_map(|idx| {
+            let nomenclature = &index.items[idx];
+            let nomenclature_ref = nomenclature.base.id.as_string();
+            if rejected.contains(&(product_ref.clone(), nomenclature_ref.clone())) {
+                return None;
+            }
+            let (rule, factor) = classify(&product_key, &index.keys[idx])?;
+            let score = candidate_score(rule, factor, &brand, stats);
+            (score >= min_score).then(|| MatchCandidateDto {
+                nomenclature_ref,
+                article: nomenclature.article.clone(),
+                description: nomenclature.base.description.clone(),
+                rule,
+                score,
+            })
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.article.cmp(&b.article))
+    });
+    candidates.truncate(MAX_CANDIDATES);
+    candidates
+}
+
+/// Товары без связи с кандидатами не ниже `min_score`, самые уверенные — первыми.
+async fn build_suggestions(
+    marketplace_id: Option<&str>,
+    min_score: f64,
+) -> Result<Vec<MatchSuggestionDto>> {
+    let products =
+        a007_marketplace_product::repository::list_for_matching(marketplace_id, true).await?;
+    let index = NomenclatureIndex::new(
+        a004_nomenclature::repository::list_all().await?,
+        p901_nomenclature_barcodes::repository::list_all(None).await?,
+    );
+    let stats = feedback_repository::counts_by_brand_and_rule().await?;
+    let rejected = feedback_repository::rejected_pairs().await?;
+
+    let mut items: Vec<MatchSuggestionDto> = products
+        .into_iter()
+        .filter_map(|product| {
+            let candidates = candidates_for(&product, &index, &stats, &rejected, min_score);
+            (!candidates.is_empty()).then(|| MatchSuggestionDto {
+                marketplace_product_ref: product.base.id.as_string(),
+                marketplace_ref: product.marketplace_ref,
+                article: product.article,
+                description: product.base.description,
+                brand: product.brand,
+                candidates,
+            })
+        })
+        .collect();
+    // Сначала самые уверенные предложения — их удобнее принимать пачкой.
+    items.sort_by(|a, b| {
+        b.candidates[0]
+            .score
+            .total_cmp(&a.candidates[0].score)
+            .then_with(|| a.article.cmp(&b.article))
+    });
+    Ok(items)
+}
+
+/// Товары без связи с номенклатурой и кандидаты для них.
+pub async fn list_suggestions(query: &MatchSuggestionQuery) -> Result<MatchSuggestionListResponse> {
+    let items = build_suggestions(
+        query.marketplace_id.as_deref(),
+        query.min_score.unwrap_or(DEFAULT_MIN_SCORE),
+    )
+    .await?;
+    let total = items.len();
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(200).min(1000);
+    let items = items.into_iter().skip(offset).take(limit).collect();
+    Ok(MatchSuggestionListResponse { items, total })
+}
+
+/// Решения «принять» для товаров, у которых лучший кандидат не ниже порога, а
+/// второй — ниже (при двух уверенных кандидатах выбор остаётся пользователю).
+fn high_confidence_decisions(
+    suggestions: &[MatchSuggestionDto],
+    threshold: f64,
+) -> Vec<MatchDecisionDto> {
+    suggestions
+        .iter()
+        .filter_map(|suggestion| {
+            let best = suggestion.candidates.first()?;
+            let runner_up = suggestion.candidates.get(1).map_or(0.0, |c| c.score);
+            (best.score >= threshold && runner_up < threshold).then(|| MatchDecisionDto {
+                marketplace_product_ref: suggestion.marketplace_product_ref.clone(),
+                nomenclature_ref: best.nomenclature_ref.clone(),
+                accept: true,
+            })
+        })
+        .collect()
+}
+
+/// Принимает все уверенные предложения (см. [`high_confidence_decisions`]).
+pub async fn accept_high_confidence(
+    request: &MatchAutoAcceptRequest,
+    decided_by: &str,
+) -> Result<MatchDecisionResponse> {
+    let threshold = request.min_score.unwrap_or(HIGH_CONFIDENCE_SCORE);
+    let suggestions = build_suggestions(
+        request.marketplace_id.as_deref(),
+        threshold.min(DEFAULT_MIN_SCORE),
+    )
+    .await?;
+    let decisions = high_confidence_decisions(&suggestions, threshold);
+    if decisions.is_empty() {
+        return Ok(MatchDecisionResponse::default());
+    }
+    apply_decisions(&MatchDecisionRequest { decisions }, decided_by).await
+}
+
+/// Применяет решения: принятые пары связываются (a007.nomenclature_ref), все решения
+/// пишутся в журнал вместе с признаками (бренд, правило, оценка на момент решения).
+pub async fn apply_decisions(
+    request: &MatchDecisionRequest,
+    decided_by: &str,
+) -> Result<MatchDecisionResponse> {
+    let nomenclature_ids: Vec<String> = request
+        .decisions
+        .iter()
+        .map(|d| d.nomenclature_ref.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let nomenclatures: HashMap<String, Nomenclature> =
+        a004_nomenclature::repository::list_by_ids(&nomenclature_ids)
+            .await?
+            .into_iter()
+            .map(|n| (n.base.id.as_string(), n))
+            .collect();
+    let mut nomenclature_barcodes: HashMap<String, Vec<String>> = HashMap::new();
+    for id in &nomenclature_ids {
+        let barcodes =
+            p901_nomenclature_barcodes::repository::get_by_nomenclature_ref(id, false).await?;
+        nomenclature_barcodes.insert(
+            id.clone(),
+            barcodes
+                .into_iter()
+                .map(|b| b.barcode.trim().to_string())
+                .collect(),
+        );
+    }
+    let stats = feedback_repository::counts_by_brand_and_rule().await?;
+    let decided_at = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+
+    let mut response = MatchDecisionResponse::default();
+    // Номенклатура, у которой поменялось число ссылок (новая и прежняя связь).
+    let mut recount: BTreeSet<String> = BTreeSet::new();
+
+    for decision in &request.decisions {
+        let pair = format!(
+            "{} → {}",
+            decision.marketplace_product_ref, decision.nomenclature_ref
+        );
+        let Ok(product_id) = Uuid::parse_str(&decision.marketplace_product_ref) else {
+            response
+                .errors
+                .push(format!("{}: некорректный id товара", pair));
+            continue;
+        };
+        let Some(mut product) = a007_marketplace_product::repository::get_by_id(product_id).await?
+        else {
+            response.errors.push(format!("{}: товар не найден", pair));
+            continue;
+        };
+        let Some(nomenclature) = nomenclatures
+            .get(&decision.nomenclature_ref)
+            .filter(|n| !n.is_folder && !n.base.metadata.is_deleted)
+        else {
+            response
+                .errors
+                .push(format!("{}: номенклатура не найдена", pair));
+            continue;
+        };
+        let nomenclature_key = MatchKey::nomenclature(
+            nomenclature,
+            nomenclature_barcodes
+                .get(&decision.nomenclature_ref)
+                .cloned()
+                .unwrap_or_default(),
+        );
+        let Some((rule, factor)) = classify(&MatchKey::product(&product), &nomenclature_key) else {
+            response.errors.push(format!(
+                "{}: пара не является предложением сопоставления",
+                pair
+            ));
+            continue;
+        };
+        let brand = brand_key(product.brand.as_deref());
+        let score = candidate_score(rule, factor, &brand, &stats);
+
+        if decision.accept {
+            if product.nomenclature_ref.as_deref() != Some(decision.nomenclature_ref.as_str()) {
+                if let Some(previous) = product.nomenclature_ref.take() {
+                    recount.insert(previous);
+                }
+                product.nomenclature_ref = Some(decision.nomenclature_ref.clone());
+                product.before_write();
+                a007_marketplace_product::repository::update(&product).await?;
+                recount.insert(decision.nomenclature_ref.clone());
+            }
+            response.accepted += 1;
+        } else {
+            response.rejected += 1;
+        }
+
+        feedback_repository::upsert(feedback_repository::Model {
+            id: feedback_repository::feedback_id(
+                &decision.marketplace_product_ref,
+                &decision.nomenclature_ref,
+            ),
+            marketplace_product_ref: decision.marketplace_product_ref.clone(),
+            nomenclature_ref: decision.nomenclature_ref.clone(),
+            decision: if decision.accept {
+                DECISION_ACCEPTED
+            } else {
+                DECISION_REJECTED
+            }
+            .to_string(),
+            rule: rule.code().to_string(),
+            brand,
+            score,
+            decided_by: Some(decided_by.to_string()),
+            decided_at: decided_at.clone(),
+        })
+        .await?;
+    }
+
+    for nomenclature_ref in recount.into_iter().filter(|r| !r.is_empty()) {
+        let Ok(id) = Uuid::parse_str(&nomenclature_ref) else {
+            continue;
+        };
+        let count = a007_marketplace_product::repository::get_by_nomenclature_ref(&nomenclature_ref)
+            .await?
+            .len() as i32;
+        a004_nomenclature::repository::update_mp_ref_count(id, count).await?;
+    }
+
+    tracing::info!(
+        "u505 suggestions: {} accepted, {} rejected, {} errors (by {})",
+        response.accepted,
+        response.rejected,
+        response.errors.len(),
+        decided_by
+    );
+    Ok(response)
+}
+
+/// Точность предложений по периодам и по правилам.
+pub async fn accuracy_report(query: &MatchAccuracyQuery) -> Result<MatchAccuracyReport> {
+    let date_from = query.date_from.as_deref().filter(|d| !d.is_empty());
+    Ok(MatchAccuracyReport {
+        periods: feedback_repository::accuracy_by_period(query.scale, date_from).await?,
+        by_rule: feedback_repository::accuracy_by_rule(date_from).await?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_are_detected_from_articles() {
+        assert_eq!(match_rule("AB-123", "ab-123"), Some(MatchRule::Exact));
+        assert_eq!(match_rule("AB 123", "ab-123"), Some(MatchRule::Normalized));
+        assert_eq!(match_rule("AB123-XL", "ab-123"), Some(MatchRule::Prefix));
+        assert_eq!(match_rule("AB1", "AB1-XL"), None, "too short for prefix");
+        assert_eq!(match_rule("AB123", "CD123"), None);
+        assert_eq!(match_rule("", "AB123"), None);
+    }
+
+    #[test]
+    fn score_moves_from_prior_towards_observed_precision() {
+        let rule = MatchRule::Prefix;
+        assert_eq!(learned_score(rule, None), rule.base_score());
+
+        let mostly_accepted = learned_score(
+            rule,
+            Some(DecisionCounts {
+                accepted: 20,
+                rejected: 1,
+            }),
+        );
+        let mostly_rejected = learned_score(
+            rule,
+            Some(DecisionCounts {
+                accepted: 1,
+                rejected: 20,
+            }),
+        );
+        assert!(mostly_accepted > 0.85);
+        assert!(mostly_rejected < DEFAULT_MIN_SCORE);
+    }
+
+    fn key(article: &str, barcodes: &[&str], name: &str) -> MatchKey {
+        MatchKey {
+            article: article.to_string(),
+            barcodes: barcodes.iter().map(|b| b.to_string()).collect(),
+            tokens: name_tokens(name),
+        }
+    }
+
+    #[test]
+    fn barcode_wins_over_article_and_name_is_last_resort() {
+        let product = key(
+            "WB-555",
+            &["4601234567890"],
+            "Футболка мужская хлопок чёрная",
+        );
+        assert_eq!(
+            classify(&product, &key("AB-123", &["4601234567890"], "Другое")),
+            Some((MatchRule::Barcode, 1.0))
+        );
+        assert_eq!(
+            classify(&product, &key("wb555", &[], "Другое")),
+            Some((MatchRule::Normalized, 1.0))
+        );
+
+        let (rule, similarity) = classify(
+            &product,
+            &key("F-01", &[], "Футболка мужская хлопок, чёрная (XL)"),
+        )
+        .unwrap();
+        assert_eq!(rule, MatchRule::Name);
+        assert!(similarity >= MIN_NAME_SIMILARITY && similarity < 1.0);
+
+        assert_eq!(
+            classify(&product, &key("F-02", &[], "Шорты детские синие")),
+            None
+        );
+    }
+
+    fn suggestion(product: &str, scores: &[f64]) -> MatchSuggestionDto {
+        MatchSuggestionDto {
+            marketplace_product_ref: product.to_string(),
+            marketplace_ref: String::new(),
+            article: String::new(),
+            description: String::new(),
+            brand: None,
+            candidates: scores
+                .iter()
+                .enumerate()
+                .map(|(i, &score)| MatchCandidateDto {
+                    nomenclature_ref: format!("{}-n{}", product, i),
+                    article: String::new(),
+                    description: String::new(),
+                    rule: MatchRule::Exact,
+                    score,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn only_unambiguous_confident_suggestions_are_auto_accepted() {
+        let suggestions = vec![
+            suggestion("p1", &[0.95, 0.5]),
+            suggestion("p2", &[0.95, 0.92]),
+            suggestion("p3", &[0.8]),
+            suggestion("p4", &[0.98]),
+        ];
+        let decisions = high_confidence_decisions(&suggestions, HIGH_CONFIDENCE_SCORE);
+        let accepted: Vec<_> = decisions
+            .iter()
+            .map(|d| {
+                (
+                    d.marketplace_product_ref.as_str(),
+                    d.nomenclature_ref.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(accepted, vec![("p1", "p1-n0"), ("p4", "p4-n0")]);
+        assert!(decisions.iter().all(|d| d.accept));
+    }
+}
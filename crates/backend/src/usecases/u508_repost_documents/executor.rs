@@ -1,4 +1,6 @@
 use super::progress_tracker::ProgressTracker;
+use super::projection_rebuild;
+use crate::shared::data::bulk_loader::BulkLoader;
 use anyhow::{anyhow, Result};
 use chrono::NaiveDate;
 use contracts::domain::common::AggregateId;
@@ -6,8 +8,10 @@ use contracts::projections::p916_mp_sales_funnel_turnovers::dto::FunnelRebuildRe
 use contracts::usecases::u508_repost_documents::{
     aggregate::AggregateOption,
     aggregate_request::AggregateRepostRequest,
+    period_request::PeriodRepostRequest,
     progress::RepostStatus,
     projection::ProjectionOption,
+    rebuild_request::ProjectionRebuildRequest,
     request::RepostRequest,
     response::{RepostResponse, RepostStartStatus},
 };
@@ -27,6 +31,18 @@ const A023_PURCHASE_OF_GOODS: &str = "a023_purchase_of_goods";
 const A026_WB_ADVERT_DAILY: &str = "a026_wb_advert_daily";
 const A034_YM_REALIZATION: &str = "a034_ym_realization";
 
+/// Порядок шагов полного перепроведения периода: сначала документы, от которых
+/// зависит себестоимость (поступления, выпуск), затем заказы → продажи (p909/p916
+/// ждут заказы), реклама и в конце реализация YM (слой ybuh сверяется с fina).
+const PERIOD_REPOST_ORDER: &[&str] = &[
+    A023_PURCHASE_OF_GOODS,
+    A021_PRODUCTION_OUTPUT,
+    A015_WB_ORDERS,
+    A012_WB_SALES,
+    A026_WB_ADVERT_DAILY,
+    A034_YM_REALIZATION,
+];
+
 pub struct RepostExecutor {
     pub progress_tracker: Arc<ProgressTracker>,
 }
@@ -167,6 +183,78 @@ impl RepostExecutor {
         })
     }
 
+    /// Запустить полный цикл «распровести → провести» документов периода по
+    /// нескольким агрегатам одной фоновой сессией (после правок логики проведения).
+    pub async fn start_period_repost(
+        &self,
+        request: PeriodRepostRequest,
+    ) -> Result<RepostResponse> {
+        Self::validate_period_request(&request)?;
+
+        let session_id = Uuid::new_v4().to_string();
+        self.progress_tracker.create_session(session_id.clone());
+
+        let executor = Arc::new(Self {
+            progress_tracker: self.progress_tracker.clone(),
+        });
+        let sid = session_id.clone();
+        let req = request.clone();
+
+        tokio::spawn(async move {
+            if let Err(error) = executor.execute_period_repost(&sid, &req).await {
+                tracing::error!("Period repost failed: {}", error);
+                executor
+                    .progress_tracker
+                    .add_error(&sid, format!("Repost failed: {}", error));
+                executor
+                    .progress_tracker
+                    .complete_session(&sid, RepostStatus::Failed);
+            }
+        });
+
+        Ok(RepostResponse {
+            session_id,
+            status: RepostStartStatus::Started,
+            message: "Period repost started".to_string(),
+        })
+    }
+
+    /// Запустить пересборку проекции p900/p904 с нуля за период: очистка строк
+    /// периода и перепроведение документов-источников одной фоновой сессией.
+    pub async fn start_projection_rebuild(
+        &self,
+        request: ProjectionRebuildRequest,
+    ) -> Result<RepostResponse> {
+        Self::validate_rebuild_request(&request)?;
+
+        let session_id = Uuid::new_v4().to_string();
+        self.progress_tracker.create_session(session_id.clone());
+
+        let executor = Arc::new(Self {
+            progress_tracker: self.progress_tracker.clone(),
+        });
+        let sid = session_id.clone();
+        let req = request.clone();
+
+        tokio::spawn(async move {
+            if let Err(error) = executor.execute_projection_rebuild(&sid, &req).await {
+                tracing::error!("Projection rebuild failed: {}", error);
+                executor
+                    .progress_tracker
+                    .add_error(&sid, format!("Rebuild failed: {}", error));
+                executor
+                    .progress_tracker
+                    .complete_session(&sid, RepostStatus::Failed);
+            }
+        });
+
+        Ok(RepostResponse {
+            session_id,
+            status: RepostStartStatus::Started,
+            message: "Projection rebuild started".to_string(),
+        })
+    }
+
     pub fn get_progress(
         &self,
         session_id: &str,
@@ -455,6 +543,42 @@ impl RepostExecutor {
         Ok(())
     }
 
+    fn validate_period_request(request: &PeriodRepostRequest) -> Result<()> {
+        if let Some(unknown) = request
+            .aggregate_keys
+            .iter()
+            .find(|key| !PERIOD_REPOST_ORDER.contains(&key.as_str()))
+        {
+            return Err(anyhow!("Unsupported aggregate_key: {}", unknown));
+        }
+
+        let date_from = NaiveDate::parse_from_str(&request.date_from, "%Y-%m-%d")
+            .map_err(|_| anyhow!("Invalid date_from: {}", request.date_from))?;
+        let date_to = NaiveDate::parse_from_str(&request.date_to, "%Y-%m-%d")
+            .map_err(|_| anyhow!("Invalid date_to: {}", request.date_to))?;
+
+        if date_from > date_to {
+            return Err(anyhow!("date_from must be less than or equal to date_to"));
+        }
+
+        Ok(())
+    }
+
+    fn validate_rebuild_request(request: &ProjectionRebuildRequest) -> Result<()> {
+        projection_rebuild::sources_for(&request.projection)?;
+
+        let date_from = NaiveDate::parse_from_str(&request.from, "%Y-%m-%d")
+            .map_err(|_| anyhow!("Invalid from: {}", request.from))?;
+        let date_to = NaiveDate::parse_from_str(&request.to, "%Y-%m-%d")
+            .map_err(|_| anyhow!("Invalid to: {}", request.to))?;
+
+        if date_from > date_to {
+            return Err(anyhow!("from must be less than or equal to to"));
+        }
+
+        Ok(())
+    }
+
     async fn execute_repost(&self, session_id: &str, request: &RepostRequest) -> Result<()> {
         let registrators = match request.projection_key.as_str() {
             P903_FINANCE_REPORT => {
@@ -654,6 +778,226 @@ impl RepostExecutor {
         Ok(())
     }
 
+    /// Пересборка проекции с нуля: документы собираются до очистки (иначе не найти
+    /// те, чьи строки уже лежат в периоде). p904 пересобирается массово через
+    /// стейджинг, остальные — очисткой периода и перепроведением по источникам.
+    /// Ошибка одного документа не останавливает прогон.
+    async fn execute_projection_rebuild(
+        &self,
+        session_id: &str,
+        request: &ProjectionRebuildRequest,
+    ) -> Result<()> {
+        let plan =
+            projection_rebuild::collect_documents(&request.projection, &request.from, &request.to)
+                .await?;
+
+        let total: usize = plan.iter().map(|(_, ids)| ids.len()).sum();
+        self.progress_tracker.set_total(session_id, total as i32);
+        self.progress_tracker
+            .set_chunks_total(session_id, plan.len() as i32);
+
+        if request.projection == projection_rebuild::P904 {
+            self.rebuild_p904_bulk(session_id, request, &plan).await?;
+        } else {
+            self.rebuild_by_reposting(session_id, request, &plan).await?;
+        }
+
+        let final_status = if self
+            .progress_tracker
+            .get_progress(session_id)
+            .map(|progress| progress.errors > 0)
+            .unwrap_or(false)
+        {
+            RepostStatus::CompletedWithErrors
+        } else {
+            RepostStatus::Completed
+        };
+
+        self.progress_tracker
+            .complete_session(session_id, final_status);
+
+        Ok(())
+    }
+
+    /// Очистка периода и перепроведение документов плана по источникам.
+    async fn rebuild_by_reposting(
+        &self,
+        session_id: &str,
+        request: &ProjectionRebuildRequest,
+        plan: &[(&'static projection_rebuild::RebuildSource, Vec<String>)],
+    ) -> Result<()> {
+        self.progress_tracker.update_progress(
+            session_id,
+            0,
+            0,
+            Some(format!("Очистка {} за период", request.projection)),
+        );
+
+        let truncated =
+            projection_rebuild::truncate_period(&request.projection, &request.from, &request.to)
+                .await?;
+        self.progress_tracker
+            .set_truncated_rows(session_id, truncated);
+
+        let mut processed = 0;
+        let mut reposted = 0;
+        for (chunk_index, (source, ids)) in plan.iter().enumerate() {
+            self.progress_tracker.update_chunk_progress(
+                session_id,
+                chunk_index as i32,
+                None,
+                None,
+                Some(source.label.to_string()),
+            );
+
+            for id in ids {
+                let current_item = format!("{} {}", source.registrator_type, id);
+                self.progress_tracker.update_progress(
+                    session_id,
+                    processed,
+                    reposted,
+                    Some(current_item.clone()),
+                );
+
+                let result = match Uuid::parse_str(id) {
+                    Ok(document_id) => dispatch_repost(source.registrator_type, document_id).await,
+                    Err(error) => Err(anyhow!("Invalid document id: {}", error)),
+                };
+                match result {
+                    Ok(()) => reposted += 1,
+                    Err(error) => self.progress_tracker.add_error(
+                        session_id,
+                        format!(
+                            "Failed to repost {} {}: {}",
+                            source.registrator_type, id, error
+                        ),
+                    ),
+                }
+
+                processed += 1;
+                self.progress_tracker.update_progress(
+                    session_id,
+                    processed,
+                    reposted,
+                    Some(current_item),
+                );
+            }
+
+            self.progress_tracker.update_chunk_progress(
+                session_id,
+                (chunk_index + 1) as i32,
+                None,
+                None,
+                Some(source.label.to_string()),
+            );
+        }
+
+        self.progress_tracker
+            .update_progress(session_id, processed, reposted, None);
+        Ok(())
+    }
+
+    /// Массовая пересборка p904: строки документов плана копятся в стейджинге, рабочая
+    /// таблица до конца не меняется; затем одна транзакция заменяет период.
+    async fn rebuild_p904_bulk(
+        &self,
+        session_id: &str,
+        request: &ProjectionRebuildRequest,
+        plan: &[(&'static projection_rebuild::RebuildSource, Vec<String>)],
+    ) -> Result<()> {
+        let mut loader =
+            BulkLoader::<crate::projections::p904_sales_data::repository::Entity>::begin().await?;
+        let mut a012_cache =
+            crate::domain::a012_wb_sales::service::PostingPreparationCache::default();
+
+        let mut processed = 0;
+        let mut built = 0;
+        for (chunk_index, (source, ids)) in plan.iter().enumerate() {
+            self.progress_tracker.update_chunk_progress(
+                session_id,
+                chunk_index as i32,
+                None,
+                None,
+                Some(source.label.to_string()),
+            );
+
+            for id in ids {
+                let current_item = format!("{} {}", source.registrator_type, id);
+                self.progress_tracker.update_progress(
+                    session_id,
+                    processed,
+                    built,
+                    Some(current_item.clone()),
+                );
+
+                let result = match Uuid::parse_str(id) {
+                    Ok(document_id) => {
+                        projection_rebuild::build_p904_rows(source, document_id, &mut a012_cache)
+                            .await
+                    }
+                    Err(error) => Err(anyhow!("Invalid document id: {}", error)),
+                };
+                match result {
+                    Ok(rows) => {
+                        loader.extend(rows).await?;
+                        built += 1;
+                    }
+                    Err(error) => self.progress_tracker.add_error(
+                        session_id,
+                        format!(
+                            "Failed to build p904 rows for {} {}: {}",
+                            source.registrator_type, id, error
+                        ),
+                    ),
+                }
+
+                processed += 1;
+                self.progress_tracker.update_progress(
+                    session_id,
+                    processed,
+                    built,
+                    Some(current_item),
+                );
+            }
+
+            self.progress_tracker.update_chunk_progress(
+                session_id,
+                (chunk_index + 1) as i32,
+                None,
+                None,
+                Some(source.label.to_string()),
+            );
+        }
+
+        self.progress_tracker.update_progress(
+            session_id,
+            processed,
+            built,
+            Some(format!(
+                "Замена {} за период: {} строк",
+                request.projection,
+                loader.len()
+            )),
+        );
+        let staged = loader.finish().await?;
+        let (deleted, inserted) =
+            projection_rebuild::swap_p904_period(staged, &request.from, &request.to).await?;
+        self.progress_tracker
+            .set_truncated_rows(session_id, deleted);
+        tracing::info!(
+            "p904 bulk rebuild {}..{}: {} documents, {} rows replaced by {}",
+            request.from,
+            request.to,
+            built,
+            deleted,
+            inserted
+        );
+
+        self.progress_tracker
+            .update_progress(session_id, processed, built, None);
+        Ok(())
+    }
+
     async fn execute_aggregate_repost(
         &self,
         session_id: &str,
@@ -663,54 +1007,14 @@ impl RepostExecutor {
             return self.execute_a012_chunked_repost(session_id, request).await;
         }
 
-        let document_ids = match request.aggregate_key.as_str() {
-            A015_WB_ORDERS => {
-                crate::domain::a015_wb_orders::repository::list_ids_by_date_range(
-                    &request.date_from,
-                    &request.date_to,
-                    request.only_posted,
-                )
-                .await?
-            }
-            A021_PRODUCTION_OUTPUT => {
-                crate::domain::a021_production_output::repository::list_ids_by_document_date_range(
-                    &request.date_from,
-                    &request.date_to,
-                    request.only_posted,
-                )
-                .await?
-            }
-            A023_PURCHASE_OF_GOODS => {
-                crate::domain::a023_purchase_of_goods::repository::list_ids_by_document_date_range(
-                    &request.date_from,
-                    &request.date_to,
-                    request.only_posted,
-                )
-                .await?
-            }
-            A026_WB_ADVERT_DAILY => {
-                crate::domain::a026_wb_advert_daily::repository::list_ids_by_period(
-                    &request.date_from,
-                    &request.date_to,
-                    request.only_posted,
-                )
-                .await?
-            }
-            A034_YM_REALIZATION => {
-                crate::domain::a034_ym_realization::repository::list_ids_by_period(
-                    &request.date_from,
-                    &request.date_to,
-                    request.only_posted,
-                )
-                .await?
-            }
-            _ => {
-                return Err(anyhow!(
-                    "Unsupported aggregate_key: {}",
-                    request.aggregate_key
-                ));
-            }
-        };
+        let document_ids = list_aggregate_ids(
+            &request.aggregate_key,
+            &request.date_from,
+            &request.date_to,
+            request.only_posted,
+            &request.connection_mp_refs,
+        )
+        .await?;
 
         let total = document_ids.len() as i32;
         self.progress_tracker.set_total(session_id, total);
@@ -799,6 +1103,126 @@ impl RepostExecutor {
         Ok(())
     }
 
+    /// Шаг = один агрегат в порядке [`PERIOD_REPOST_ORDER`]. Каждый документ
+    /// распроводится и сразу проводится заново; ошибка одного документа попадает
+    /// в сессию и не прерывает прогон. Документы идут последовательно, чтобы
+    /// следующий шаг видел уже пересобранные движения предыдущего.
+    async fn execute_period_repost(
+        &self,
+        session_id: &str,
+        request: &PeriodRepostRequest,
+    ) -> Result<()> {
+        let steps: Vec<&str> = PERIOD_REPOST_ORDER
+            .iter()
+            .copied()
+            .filter(|key| {
+                request.aggregate_keys.is_empty()
+                    || request
+                        .aggregate_keys
+                        .iter()
+                        .any(|selected| selected == key)
+            })
+            .collect();
+
+        let mut prepared_steps = Vec::with_capacity(steps.len());
+        let mut total_documents = 0_i32;
+        for aggregate_key in steps {
+            let ids = list_aggregate_ids(
+                aggregate_key,
+                &request.date_from,
+                &request.date_to,
+                request.only_posted,
+                &request.connection_mp_refs,
+            )
+            .await?;
+            total_documents += ids.len() as i32;
+            prepared_steps.push((aggregate_key, ids));
+        }
+
+        let steps_total = prepared_steps.len();
+        self.progress_tracker.set_total(session_id, total_documents);
+        self.progress_tracker
+            .set_chunks_total(session_id, steps_total as i32);
+
+        let mut processed = 0_i32;
+        let mut reposted = 0_i32;
+
+        for (step_index, (aggregate_key, ids)) in prepared_steps.iter().enumerate() {
+            self.progress_tracker.update_chunk_progress(
+                session_id,
+                step_index as i32,
+                None,
+                None,
+                Some(format!(
+                    "Шаг {}/{}: {} ({} док.)",
+                    step_index + 1,
+                    steps_total,
+                    aggregate_key,
+                    ids.len()
+                )),
+            );
+
+            for document_id in ids {
+                let current_item = format!("{} {}", aggregate_key, document_id);
+                self.progress_tracker.update_progress(
+                    session_id,
+                    processed,
+                    reposted,
+                    Some(current_item.clone()),
+                );
+
+                match Uuid::parse_str(document_id) {
+                    Ok(aggregate_id) => {
+                        match repost_cycle_with_retry(aggregate_key, aggregate_id).await {
+                            Ok(()) => reposted += 1,
+                            Err(error) => self
+                                .progress_tracker
+                                .add_error(session_id, format!("{}: {}", current_item, error)),
+                        }
+                    }
+                    Err(error) => self.progress_tracker.add_error(
+                        session_id,
+                        format!("Invalid aggregate id {}: {}", document_id, error),
+                    ),
+                }
+
+                processed += 1;
+                self.progress_tracker.update_progress(
+                    session_id,
+                    processed,
+                    reposted,
+                    Some(current_item),
+                );
+            }
+        }
+
+        self.progress_tracker
+            .update_progress(session_id, processed, reposted, None);
+        self.progress_tracker.update_chunk_progress(
+            session_id,
+            steps_total as i32,
+            None,
+            None,
+            Some("Готово".to_string()),
+        );
+
+        let final_status = if self
+            .progress_tracker
+            .get_progress(session_id)
+            .map(|progress| progress.errors > 0)
+            .unwrap_or(false)
+        {
+            RepostStatus::CompletedWithErrors
+        } else {
+            RepostStatus::Completed
+        };
+
+        self.progress_tracker
+            .complete_session(session_id, final_status);
+
+        Ok(())
+    }
+
     async fn execute_a012_chunked_repost(
         &self,
         session_id: &str,
@@ -1065,6 +1489,130 @@ async fn dispatch_aggregate_repost_with_retry(
     }
 }
 
+/// Документы агрегата за период, которые перепроводит u508.
+async fn list_aggregate_ids(
+    aggregate_key: &str,
+    date_from: &str,
+    date_to: &str,
+    only_posted: bool,
+    connection_mp_refs: &[String],
+) -> Result<Vec<String>> {
+    match aggregate_key {
+        A012_WB_SALES => {
+            let chunks =
+                crate::domain::a012_wb_sales::repository::list_repost_chunks_by_sale_date_range(
+                    date_from,
+                    date_to,
+                    only_posted,
+                    connection_mp_refs,
+                )
+                .await?;
+            let mut ids = Vec::new();
+            for chunk in chunks {
+                ids.extend(
+                    crate::domain::a012_wb_sales::repository::list_ids_by_sale_date_and_connection(
+                        &chunk.sale_date,
+                        &chunk.connection_mp_ref,
+                        only_posted,
+                    )
+                    .await?,
+                );
+            }
+            Ok(ids)
+        }
+        A015_WB_ORDERS => {
+            crate::domain::a015_wb_orders::repository::list_ids_by_date_range(
+                date_from,
+                date_to,
+                only_posted,
+            )
+            .await
+        }
+        A021_PRODUCTION_OUTPUT => {
+            crate::domain::a021_production_output::repository::list_ids_by_document_date_range(
+                date_from,
+                date_to,
+                only_posted,
+            )
+            .await
+        }
+        A023_PURCHASE_OF_GOODS => {
+            crate::domain::a023_purchase_of_goods::repository::list_ids_by_document_date_range(
+                date_from,
+                date_to,
+                only_posted,
+            )
+            .await
+        }
+        A026_WB_ADVERT_DAILY => {
+            crate::domain::a026_wb_advert_daily::repository::list_ids_by_period(
+                date_from,
+                date_to,
+                only_posted,
+            )
+            .await
+        }
+        A034_YM_REALIZATION => {
+            crate::domain::a034_ym_realization::repository::list_ids_by_period(
+                date_from,
+                date_to,
+                only_posted,
+            )
+            .await
+        }
+        _ => Err(anyhow!("Unsupported aggregate_key: {}", aggregate_key)),
+    }
+}
+
+/// Распровести и сразу провести документ, с повторами при блокировке SQLite.
+///
+/// Если распроведение не удалось, документ остаётся как был. Если не удалось
+/// проведение после успешного распроведения — документ остаётся распроведённым,
+/// и это явно попадает в текст ошибки сессии.
+async fn repost_cycle_with_retry(aggregate_key: &str, aggregate_id: Uuid) -> Result<()> {
+    const MAX_ATTEMPTS: u32 = 8;
+
+    let mut attempt = 0u32;
+    loop {
+        attempt += 1;
+        match dispatch_aggregate_unpost(aggregate_key, aggregate_id).await {
+            Ok(()) => break,
+            Err(error) if attempt < MAX_ATTEMPTS && is_database_locked(&error) => {
+                let base_ms = 20u64 * attempt as u64;
+                let jitter_ms = (aggregate_id.as_u128() as u64 % 40) + 1;
+                tokio::time::sleep(std::time::Duration::from_millis(base_ms + jitter_ms)).await;
+            }
+            Err(error) => return Err(anyhow!("распроведение: {}", error)),
+        }
+    }
+
+    dispatch_aggregate_repost_with_retry(aggregate_key, aggregate_id)
+        .await
+        .map_err(|error| anyhow!("проведение (документ остался распроведённым): {}", error))
+}
+
+async fn dispatch_aggregate_unpost(aggregate_key: &str, aggregate_id: Uuid) -> Result<()> {
+    match aggregate_key {
+        A012_WB_SALES => crate::domain::a012_wb_sales::posting::unpost_document(aggregate_id).await,
+        A015_WB_ORDERS => {
+            crate::domain::a015_wb_orders::posting::unpost_document(aggregate_id).await
+        }
+        A021_PRODUCTION_OUTPUT => {
+            crate::domain::a021_production_output::service::unpost_document(aggregate_id).await
+        }
+        A023_PURCHASE_OF_GOODS => {
+            crate::domain::a023_purchase_of_goods::service::unpost_document(aggregate_id).await
+        }
+        A026_WB_ADVERT_DAILY => {
+            crate::domain::a026_wb_advert_daily::posting::unpost_document(aggregate_id).await
+        }
+        A034_YM_REALIZATION => {
+            crate::domain::a034_ym_realization::posting::unpost_document(aggregate_id).await
+        }
+        _ => Err(anyhow!("Unsupported aggregate_key: {}", aggregate_key)),
+    }
+}
+
 async fn dispatch_aggregate_repost(aggregate_key: &str, aggregate_id: Uuid) -> Result<()> {
     match aggregate_key {
         A012_WB_SALES => crate::domain::a012_wb_sales::posting::post_document(aggregate_id).await,
@@ -1,5 +1,6 @@
 pub mod executor;
 pub mod progress_tracker;
+pub mod projection_rebuild;
 
 pub use executor::RepostExecutor;
 pub use progress_tracker::ProgressTracker;
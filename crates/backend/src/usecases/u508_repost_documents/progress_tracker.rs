@@ -87,6 +87,14 @@ impl ProgressTracker {
         }
     }
 
+    pub fn set_truncated_rows(&self, session_id: &str, rows: u64) {
+        let mut sessions = self.sessions.write().unwrap();
+        if let Some(progress) = sessions.get_mut(session_id) {
+            progress.truncated_rows = rows;
+            progress.updated_at = chrono::Utc::now();
+        }
+    }
+
     pub fn add_error(&self, session_id: &str, message: String) {
         let mut sessions = self.sessions.write().unwrap();
         if let Some(progress) = sessions.get_mut(session_id) {
@@ -0,0 +1,279 @@
+//! Пересборка проекций p900/p904 с нуля за период.
+//!
+//! После правок логики проведения строки проекции за период удаляются целиком
+//! (вместе с архивом), а затем перепроводятся проведённые документы-источники —
+//! проекция строится тем же кодом, что и при обычном проведении. В пересборку
+//! попадают документы с датой события в периоде и документы, у которых до
+//! очистки были строки в периоде (дата события могла сдвинуться после правок).
+//! Непроведённые и удалённые документы не трогаются: их «висящие» строки
+//! просто исчезают вместе с очисткой.
+//!
+//! p904 пересобирается массово: строки документов строятся теми же построителями
+//! (с той же подготовкой документа), копятся в стейджинге `BulkLoader` и
+//! заменяют период одной транзакцией вместе с пересчётом p919. Документы и
+//! остальные проекции при этом не перезаписываются — для них есть перепроведение.
+
+use anyhow::{anyhow, Result};
+use sea_orm::{ConnectionTrait, Statement, TransactionTrait};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::domain::a012_wb_sales::service::PostingPreparationCache;
+use crate::projections::p904_sales_data::repository::Model as P904Model;
+use crate::shared::data::bulk_loader::StagedRows;
+use crate::shared::data::db::get_connection;
+
+pub const P900: &str = "p900";
+pub const P904: &str = "p904";
+
+/// Агрегат, документы которого пишут строки в p900/p904.
+pub struct RebuildSource {
+    /// `registrator_type` в p904 (он же ключ перепроведения документа)
+    pub registrator_type: &'static str,
+    /// `document_type` в p900; `None` — агрегат в p900 не пишет
+    pub p900_document_type: Option<&'static str>,
+    pub label: &'static str,
+    pub table: &'static str,
+    /// Дата события `YYYY-MM-DD` из тех же полей, что берут построители проекций
+    pub date_expr: &'static str,
+}
+
+/// Источники в порядке перепроведения.
+pub const SOURCES: &[RebuildSource] = &[
+    RebuildSource {
+        registrator_type: "WB_Sales",
+        p900_document_type: Some("WB_Sales"),
+        label: "a012 — WB Sales",
+        table: "a012_wb_sales",
+        date_expr: "substr(sale_date, 1, 10)",
+    },
+    RebuildSource {
+        registrator_type: "OZON_FBS",
+        p900_document_type: Some("OZON_FBS_Posting"),
+        label: "a010 — OZON FBS",
+        table: "a010_ozon_fbs_posting",
+        date_expr: "substr(COALESCE(json_extract(state_json, '$.delivered_at'), \
+                    json_extract(state_json, '$.updated_at_source')), 1, 10)",
+    },
+    RebuildSource {
+        registrator_type: "OZON_FBO",
+        p900_document_type: Some("OZON_FBO_Posting"),
+        label: "a011 — OZON FBO",
+        table: "a011_ozon_fbo_posting",
+        date_expr: "substr(COALESCE(json_extract(state_json, '$.delivered_at'), \
+                    json_extract(state_json, '$.updated_at_source'), \
+                    json_extract(state_json, '$.created_at')), 1, 10)",
+    },
+    RebuildSource {
+        registrator_type: "OZON_Returns",
+        p900_document_type: Some("OZON_Returns"),
+        label: "a009 — OZON Returns",
+        table: "a009_ozon_returns",
+        date_expr: "substr(return_date, 1, 10)",
+    },
+    RebuildSource {
+        registrator_type: "OZON_Transactions",
+        p900_document_type: None,
+        label: "a014 — OZON Transactions",
+        table: "a014_ozon_transactions",
+        date_expr: "substr(json_extract(header_json, '$.operation_date'), 1, 10)",
+    },
+    RebuildSource {
+        registrator_type: "YM_Order",
+        p900_document_type: Some("YM_Order"),
+        label: "a013 — YM Order",
+        table: "a013_ym_order",
+        date_expr: "substr(COALESCE(delivery_date, status_changed_at), 1, 10)",
+    },
+    RebuildSource {
+        registrator_type: "YM_Returns",
+        p900_document_type: None,
+        label: "a016 — YM Returns",
+        table: "a016_ym_returns",
+        date_expr: "substr(COALESCE(json_extract(state_json, '$.refund_date'), \
+                    json_extract(state_json, '$.created_at_source')), 1, 10)",
+    },
+];
+
+/// Источники проекции; для неизвестного ключа — ошибка.
+pub fn sources_for(projection: &str) -> Result<Vec<&'static RebuildSource>> {
+    match projection {
+        P900 => Ok(SOURCES
+            .iter()
+            .filter(|s| s.p900_document_type.is_some())
+            .collect()),
+        P904 => Ok(SOURCES.iter().collect()),
+        _ => Err(anyhow!("Unsupported projection: {}", projection)),
+    }
+}
+
+/// Документы с записями проекции за период: `(registrator_type, registrator_ref)`.
+async fn projection_registrators(
+    projection: &str,
+    date_from: &str,
+    date_to: &str,
+) -> Result<Vec<(String, String)>> {
+    match projection {
+        P900 => {
+            let rows = crate::projections::p900_mp_sales_register::repository::list_registrators_by_period(
+                date_from, date_to,
+            )
+            .await?;
+            Ok(rows
+                .into_iter()
+                .filter_map(|(document_type, registrator_ref)| {
+                    let source = SOURCES
+                        .iter()
+                        .find(|s| s.p900_document_type == Some(document_type.as_str()))?;
+                    Some((source.registrator_type.to_string(), registrator_ref))
+                })
+                .collect())
+        }
+        P904 => {
+            let rows =
+                crate::projections::p904_sales_data::repository::list_registrators_by_period(
+                    date_from, date_to,
+                )
+                .await?;
+            Ok(rows
+                .into_iter()
+                .map(|r| (r.registrator_type, r.registrator_ref))
+                .collect())
+        }
+        _ => Err(anyhow!("Unsupported projection: {}", projection)),
+    }
+}
+
+async fn query_ids(sql: String, values: Vec<sea_orm::Value>) -> Result<Vec<String>> {
+    let db = get_connection();
+    let rows = db
+        .query_all(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| row.try_get::<String>("", "id").ok())
+        .collect())
+}
+
+/// Проведённые документы источника с датой события в периоде.
+async fn list_posted_ids_by_period(
+    source: &RebuildSource,
+    date_from: &str,
+    date_to: &str,
+) -> Result<Vec<String>> {
+    let sql = format!(
+        "SELECT id FROM {table} WHERE is_deleted = 0 AND is_posted = 1 \
+         AND {date} >= ? AND {date} <= ? ORDER BY {date}, id",
+        table = source.table,
+        date = source.date_expr
+    );
+    query_ids(sql, vec![date_from.into(), date_to.into()]).await
+}
+
+/// Оставить из `ids` проведённые и не удалённые документы источника.
+async fn filter_posted_ids(source: &RebuildSource, ids: &[String]) -> Result<Vec<String>> {
+    const CHUNK: usize = 500;
+
+    let mut posted = Vec::new();
+    for chunk in ids.chunks(CHUNK) {
+        let placeholders = vec!["?"; chunk.len()].join(", ");
+        let sql = format!(
+            "SELECT id FROM {} WHERE is_deleted = 0 AND is_posted = 1 AND id IN ({})",
+            source.table, placeholders
+        );
+        let values = chunk.iter().map(|id| id.clone().into()).collect();
+        posted.extend(query_ids(sql, values).await?);
+    }
+    Ok(posted)
+}
+
+/// Документы к перепроведению по источникам (в порядке `SOURCES`).
+///
+/// Считается до очистки: после неё строки проекции за период уже не найти.
+pub async fn collect_documents(
+    projection: &str,
+    date_from: &str,
+    date_to: &str,
+) -> Result<Vec<(&'static RebuildSource, Vec<String>)>> {
+    let registrators = projection_registrators(projection, date_from, date_to).await?;
+
+    let mut result = Vec::new();
+    for source in sources_for(projection)? {
+        let mut ids = list_posted_ids_by_period(source, date_from, date_to).await?;
+        let mut seen: HashSet<String> = ids.iter().cloned().collect();
+
+        let shifted: Vec<String> = registrators
+            .iter()
+            .filter(|(registrator_type, registrator_ref)| {
+                registrator_type == source.registrator_type && !seen.contains(registrator_ref)
+            })
+            .map(|(_, registrator_ref)| registrator_ref.clone())
+            .collect();
+        for id in filter_posted_ids(source, &shifted).await? {
+            if seen.insert(id.clone()) {
+                ids.push(id);
+            }
+        }
+
+        result.push((source, ids));
+    }
+    Ok(result)
+}
+
+/// Удалить строки проекции за период (рабочая таблица и архив).
+pub async fn truncate_period(projection: &str, date_from: &str, date_to: &str) -> Result<u64> {
+    match projection {
+        P900 => {
+            crate::projections::p900_mp_sales_register::repository::delete_by_period(
+                date_from, date_to,
+            )
+            .await
+        }
+        P904 => {
+            crate::projections::p904_sales_data::repository::delete_by_period(date_from, date_to)
+                .await
+        }
+        _ => Err(anyhow!("Unsupported projection: {}", projection)),
+    }
+}
+
+/// Строки p904 документа источника без перепроведения (массовая пересборка).
+pub async fn build_p904_rows(
+    source: &RebuildSource,
+    id: Uuid,
+    a012_cache: &mut PostingPreparationCache,
+) -> Result<Vec<P904Model>> {
+    match source.registrator_type {
+        "WB_Sales" => crate::domain::a012_wb_sales::posting::build_p904_rows(id, a012_cache).await,
+        "OZON_FBS" => crate::domain::a010_ozon_fbs_posting::posting::build_p904_rows(id).await,
+        "OZON_FBO" => crate::domain::a011_ozon_fbo_posting::posting::build_p904_rows(id).await,
+        "OZON_Returns" => crate::domain::a009_ozon_returns::posting::build_p904_rows(id).await,
+        "OZON_Transactions" => {
+            crate::domain::a014_ozon_transactions::posting::build_p904_rows(id).await
+        }
+        "YM_Order" => crate::domain::a013_ym_order::posting::build_p904_rows(id).await,
+        "YM_Returns" => crate::domain::a016_ym_returns::posting::build_p904_rows(id).await,
+        other => Err(anyhow!("Unsupported registrator_type: {}", other)),
+    }
+}
+
+/// Заменяет строки p904 периода загруженными и пересчитывает p919 (она строится
+/// из p904) — одной транзакцией. Возвращает (удалено, вставлено).
+pub async fn swap_p904_period(
+    staged: StagedRows,
+    date_from: &str,
+    date_to: &str,
+) -> Result<(u64, u64)> {
+    let txn = get_connection().begin().await?;
+    let counts = crate::projections::p904_sales_data::repository::replace_from_staged_with_conn(
+        &txn, &staged, date_from, date_to,
+    )
+    .await?;
+    crate::projections::p919_daily_sales_summary::repository::rebuild_with_conn(&txn).await?;
+    txn.commit().await?;
+    Ok(counts)
+}
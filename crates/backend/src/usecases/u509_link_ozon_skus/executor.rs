@@ -0,0 +1,311 @@
+use super::progress_tracker::ProgressTracker;
+use crate::domain::a014_ozon_transactions::{repository, sku_linking};
+use crate::shared::data::db::get_connection;
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use contracts::usecases::u509_link_ozon_skus::{
+    progress::LinkStatus,
+    report::{UnmatchedSkuReport, UnmatchedSkuRow},
+    request::LinkRequest,
+    response::{LinkResponse, LinkStartStatus},
+};
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Строка `items_json` без ссылки на a007: пустая ссылка или не-uuid (ранние
+/// проведения писали сюда `offer_id`). Строки без SKU не учитываются.
+const UNLINKED_ITEM_SQL: &str = "COALESCE(json_extract(i.value, '$.sku'), 0) <> 0 \
+     AND COALESCE(json_extract(i.value, '$.marketplace_product_ref'), '') \
+     NOT LIKE '________-____-____-____-____________'";
+
+/// Максимум строк в отчёте по несопоставленным SKU.
+const UNMATCHED_REPORT_LIMIT: usize = 500;
+
+pub struct LinkExecutor {
+    pub progress_tracker: Arc<ProgressTracker>,
+}
+
+impl LinkExecutor {
+    pub fn new(progress_tracker: Arc<ProgressTracker>) -> Self {
+        Self { progress_tracker }
+    }
+
+    pub fn get_progress(
+        &self,
+        session_id: &str,
+    ) -> Option<contracts::usecases::u509_link_ozon_skus::progress::LinkProgress> {
+        self.progress_tracker.get_progress(session_id)
+    }
+
+    pub async fn start_linking(&self, request: LinkRequest) -> Result<LinkResponse> {
+        let (where_sql, values) = build_filter(&request)?;
+
+        let session_id = Uuid::new_v4().to_string();
+        self.progress_tracker.create_session(session_id.clone());
+
+        let executor = Arc::new(Self {
+            progress_tracker: self.progress_tracker.clone(),
+        });
+        let sid = session_id.clone();
+        let relink = request.relink_existing;
+
+        tokio::spawn(async move {
+            if let Err(error) = executor
+                .execute_linking(&sid, &where_sql, values, relink)
+                .await
+            {
+                tracing::error!("Ozon SKU linking failed: {}", error);
+                executor
+                    .progress_tracker
+                    .add_error(&sid, format!("Linking failed: {}", error));
+                executor
+                    .progress_tracker
+                    .complete_session(&sid, LinkStatus::Failed);
+            }
+        });
+
+        Ok(LinkResponse {
+            session_id,
+            status: LinkStartStatus::Started,
+            message: "Linking started".to_string(),
+        })
+    }
+
+    async fn execute_linking(
+        &self,
+        session_id: &str,
+        where_sql: &str,
+        values: Vec<Value>,
+        relink: bool,
+    ) -> Result<()> {
+        let sql = format!(
+            "SELECT t.id AS id FROM a014_ozon_transactions t WHERE {} \
+             ORDER BY json_extract(t.header_json, '$.operation_date')",
+            where_sql
+        );
+        let rows = get_connection()
+            .query_all(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                sql,
+                values,
+            ))
+            .await?;
+        let ids = rows
+            .iter()
+            .map(|row| row.try_get::<String>("", "id"))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        self.progress_tracker
+            .set_total(session_id, ids.len() as i32);
+
+        for id in ids {
+            match link_document(&id, relink).await {
+                Ok((label, updated, linked, unmatched)) => {
+                    self.progress_tracker.record_document(
+                        session_id,
+                        updated,
+                        linked as i32,
+                        unmatched as i32,
+                        Some(label),
+                    );
+                }
+                Err(error) => {
+                    tracing::warn!("Failed to link SKUs of a014 {}: {}", id, error);
+                    self.progress_tracker
+                        .add_error(session_id, format!("{}: {}", id, error));
+                    self.progress_tracker
+                        .record_document(session_id, false, 0, 0, Some(id));
+                }
+            }
+        }
+
+        let has_errors = self
+            .progress_tracker
+            .get_progress(session_id)
+            .map(|p| p.errors > 0)
+            .unwrap_or(false);
+        self.progress_tracker.complete_session(
+            session_id,
+            if has_errors {
+                LinkStatus::CompletedWithErrors
+            } else {
+                LinkStatus::Completed
+            },
+        );
+        Ok(())
+    }
+
+    /// SKU из непривязанных строк транзакций, сгруппированные по подключению.
+    pub async fn unmatched_report(&self) -> Result<UnmatchedSkuReport> {
+        let db = get_connection();
+        let sql = format!(
+            "SELECT json_extract(t.header_json, '$.connection_id') AS connection_id, \
+                    json_extract(i.value, '$.sku') AS sku, \
+                    MAX(json_extract(i.value, '$.name')) AS name, \
+                    COUNT(*) AS items, \
+                    COUNT(DISTINCT t.id) AS documents, \
+                    MAX(json_extract(t.header_json, '$.operation_date')) AS last_operation_date \
+             FROM a014_ozon_transactions t, json_each(t.items_json) i \
+             WHERE t.is_deleted = 0 AND {} \
+             GROUP BY 1, 2 \
+             ORDER BY items DESC, sku \
+             LIMIT {}",
+            UNLINKED_ITEM_SQL,
+            UNMATCHED_REPORT_LIMIT + 1
+        );
+        let result = db
+            .query_all(Statement::from_string(DatabaseBackend::Sqlite, sql))
+            .await?;
+        let mut rows = Vec::with_capacity(result.len());
+        for row in result {
+            rows.push(UnmatchedSkuRow {
+                connection_id: row
+                    .try_get::<Option<String>>("", "connection_id")?
+                    .unwrap_or_default(),
+                sku: row.try_get("", "sku")?,
+                name: row
+                    .try_get::<Option<String>>("", "name")?
+                    .unwrap_or_default(),
+                items: row.try_get("", "items")?,
+                documents: row.try_get("", "documents")?,
+                last_operation_date: row.try_get("", "last_operation_date")?,
+            });
+        }
+        let truncated = rows.len() > UNMATCHED_REPORT_LIMIT;
+        rows.truncate(UNMATCHED_REPORT_LIMIT);
+
+        let total_sql = format!(
+            "SELECT COUNT(*) AS cnt FROM a014_ozon_transactions t, json_each(t.items_json) i \
+             WHERE t.is_deleted = 0 AND {}",
+            UNLINKED_ITEM_SQL
+        );
+        let total_items = match db
+            .query_one(Statement::from_string(DatabaseBackend::Sqlite, total_sql))
+            .await?
+        {
+            Some(row) => row.try_get("", "cnt")?,
+            None => 0,
+        };
+
+        Ok(UnmatchedSkuReport {
+            rows,
+            total_items,
+            truncated,
+        })
+    }
+}
+
+/// Привязка строк одного документа: (номер операции, сохранён ли, привязано, не найдено).
+async fn link_document(id: &str, relink: bool) -> Result<(String, bool, usize, usize)> {
+    let uuid = Uuid::parse_str(id).map_err(|e| anyhow!("Invalid id: {}", e))?;
+    let mut document = repository::get_by_id(uuid)
+        .await?
+        .ok_or_else(|| anyhow!("Document not found"))?;
+    let label = document.header.operation_id.to_string();
+
+    let outcome = sku_linking::link_items(&mut document, relink).await?;
+    let updated = outcome.linked > 0;
+    if updated {
+        document.before_write();
+        repository::update(&document).await?;
+    }
+    Ok((label, updated, outcome.linked, outcome.unmatched.len()))
+}
+
+/// Условие отбора документов a014 и его параметры.
+fn build_filter(request: &LinkRequest) -> Result<(String, Vec<Value>)> {
+    let mut clauses = vec!["t.is_deleted = 0".to_string()];
+    let mut values: Vec<Value> = Vec::new();
+
+    if let Some(connection_id) = request
+        .connection_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    {
+        clauses.push("json_extract(t.header_json, '$.connection_id') = ?".to_string());
+        values.push(connection_id.to_string().into());
+    }
+
+    let date_from = parse_date(request.date_from.as_deref(), "date_from")?;
+    let date_to = parse_date(request.date_to.as_deref(), "date_to")?;
+    if let (Some(from), Some(to)) = (date_from, date_to) {
+        if from > to {
+            return Err(anyhow!("Invalid period: date_from is after date_to"));
+        }
+    }
+    if let Some(from) = date_from {
+        clauses.push(
+            "substr(json_extract(t.header_json, '$.operation_date'), 1, 10) >= ?".to_string(),
+        );
+        values.push(from.format("%Y-%m-%d").to_string().into());
+    }
+    if let Some(to) = date_to {
+        clauses.push(
+            "substr(json_extract(t.header_json, '$.operation_date'), 1, 10) <= ?".to_string(),
+        );
+        values.push(to.format("%Y-%m-%d").to_string().into());
+    }
+
+    if !request.relink_existing {
+        clauses.push(format!(
+            "EXISTS (SELECT 1 FROM json_each(t.items_json) i WHERE {})",
+            UNLINKED_ITEM_SQL
+        ));
+    }
+
+    Ok((clauses.join(" AND "), values))
+}
+
+fn parse_date(value: Option<&str>, field: &str) -> Result<Option<NaiveDate>> {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        None => Ok(None),
+        Some(v) => NaiveDate::parse_from_str(v, "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| anyhow!("Invalid {}: {}", field, v)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_request_selects_only_documents_with_unlinked_items() {
+        let (sql, values) = build_filter(&LinkRequest::default()).unwrap();
+        assert!(sql.starts_with("t.is_deleted = 0"));
+        assert!(sql.contains("EXISTS"));
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn relink_with_filters_binds_connection_and_period() {
+        let request = LinkRequest {
+            connection_id: Some(" conn-1 ".to_string()),
+            date_from: Some("2025-01-01".to_string()),
+            date_to: Some("2025-01-31".to_string()),
+            relink_existing: true,
+        };
+        let (sql, values) = build_filter(&request).unwrap();
+        assert!(!sql.contains("EXISTS"));
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0], Value::from("conn-1".to_string()));
+    }
+
+    #[test]
+    fn rejects_bad_dates_and_inverted_period() {
+        let bad = LinkRequest {
+            date_from: Some("01.01.2025".to_string()),
+            ..Default::default()
+        };
+        assert!(build_filter(&bad).is_err());
+
+        let inverted = LinkRequest {
+            date_from: Some("2025-02-01".to_string()),
+            date_to: Some("2025-01-01".to_string()),
+            ..Default::default()
+        };
+        let err = build_filter(&inverted).unwrap_err().to_string();
+        assert!(err.contains("Invalid"));
+    }
+}
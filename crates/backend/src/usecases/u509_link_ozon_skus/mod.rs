@@ -0,0 +1,5 @@
+pub mod executor;
+pub mod progress_tracker;
+
+pub use executor::LinkExecutor;
+pub use progress_tracker::ProgressTracker;
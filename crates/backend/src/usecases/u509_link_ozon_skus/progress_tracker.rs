@@ -0,0 +1,75 @@
+use contracts::usecases::u509_link_ozon_skus::progress::{LinkProgress, LinkStatus};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+#[derive(Clone)]
+pub struct ProgressTracker {
+    sessions: Arc<RwLock<HashMap<String, LinkProgress>>>,
+}
+
+impl ProgressTracker {
+    pub fn new() -> Self {
+        Self {
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub fn create_session(&self, session_id: String) {
+        let mut sessions = self.sessions.write().unwrap();
+        sessions.insert(session_id.clone(), LinkProgress::new(session_id, None));
+    }
+
+    pub fn get_progress(&self, session_id: &str) -> Option<LinkProgress> {
+        self.sessions.read().unwrap().get(session_id).cloned()
+    }
+
+    pub fn set_total(&self, session_id: &str, total: i32) {
+        let mut sessions = self.sessions.write().unwrap();
+        if let Some(progress) = sessions.get_mut(session_id) {
+            progress.total = Some(total);
+        }
+    }
+
+    pub fn record_document(
+        &self,
+        session_id: &str,
+        updated: bool,
+        items_linked: i32,
+        items_unmatched: i32,
+        current_item: Option<String>,
+    ) {
+        let mut sessions = self.sessions.write().unwrap();
+        if let Some(progress) = sessions.get_mut(session_id) {
+            progress.processed += 1;
+            if updated {
+                progress.documents_updated += 1;
+            }
+            progress.items_linked += items_linked;
+            progress.items_unmatched += items_unmatched;
+            progress.current_item = current_item;
+        }
+    }
+
+    pub fn add_error(&self, session_id: &str, message: String) {
+        let mut sessions = self.sessions.write().unwrap();
+        if let Some(progress) = sessions.get_mut(session_id) {
+            progress.errors += 1;
+            progress.error_messages.push(message);
+        }
+    }
+
+    pub fn complete_session(&self, session_id: &str, status: LinkStatus) {
+        let mut sessions = self.sessions.write().unwrap();
+        if let Some(progress) = sessions.get_mut(session_id) {
+            progress.status = status;
+            progress.completed_at = Some(chrono::Utc::now());
+            progress.current_item = None;
+        }
+    }
+}
+
+impl Default for ProgressTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
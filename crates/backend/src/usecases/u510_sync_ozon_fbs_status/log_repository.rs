@@ -0,0 +1,50 @@
+//! Журнал смен статуса FBS отправлений (`u510_fbs_status_log`): одна строка на
+//! каждую обнаруженную синхронизацией смену статуса или подстатуса.
+
+use anyhow::Result;
+use sea_orm::entity::prelude::*;
+use sea_orm::{EntityTrait, Set};
+
+use crate::shared::data::db::get_connection;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "u510_fbs_status_log")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub connection_id: String,
+    pub document_ref: String,
+    pub document_no: String,
+    pub old_status: String,
+    pub new_status: String,
+    #[sea_orm(nullable)]
+    pub old_substatus: Option<String>,
+    #[sea_orm(nullable)]
+    pub new_substatus: Option<String>,
+    pub synced_at: String,
+    #[sea_orm(nullable)]
+    pub session_id: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+pub async fn insert(entry: Model) -> Result<()> {
+    Entity::insert(ActiveModel {
+        id: Set(entry.id),
+        connection_id: Set(entry.connection_id),
+        document_ref: Set(entry.document_ref),
+        document_no: Set(entry.document_no),
+        old_status: Set(entry.old_status),
+        new_status: Set(entry.new_status),
+        old_substatus: Set(entry.old_substatus),
+        new_substatus: Set(entry.new_substatus),
+        synced_at: Set(entry.synced_at),
+        session_id: Set(entry.session_id),
+    })
+    .exec(get_connection())
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,10 @@
+//! u510: инкрементальная синхронизация статусов OZON FBS отправлений (a010).
+//!
+//! Импорт u502 загружает отправления по дате создания и к старым документам не
+//! возвращается, а статус у Ozon меняется позже (доставлено, отменено, возврат).
+//! Здесь запрашиваются отправления со сменой статуса за окно от watermark задачи
+//! task033; у найденных документов обновляется `state`, документ перепроводится,
+//! а каждая смена пишется в журнал `u510_fbs_status_log`.
+
+pub mod log_repository;
+pub mod sync;
@@ -0,0 +1,218 @@
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::a010_ozon_fbs_posting::aggregate::{OzonFbsPosting, OzonFbsPostingState};
+use contracts::domain::common::AggregateId;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::log_repository;
+use crate::domain::a010_ozon_fbs_posting;
+use crate::usecases::u502_import_from_ozon::ozon_api_client::{OzonApiClient, OzonPosting};
+use crate::usecases::u502_import_from_ozon::processors::postings::normalize_ozon_status;
+
+/// Размер страницы v3/posting/fbs/list (максимум API).
+const PAGE_LIMIT: i32 = 1000;
+
+/// Окно синхронизации.
+#[derive(Debug, Clone, Copy)]
+pub struct SyncWindow {
+    /// Нижняя граница даты создания отправлений (обязательный фильтр API)
+    pub created_since: DateTime<Utc>,
+    /// Период смены статуса
+    pub changed_from: DateTime<Utc>,
+    pub changed_to: DateTime<Utc>,
+}
+
+/// Итог синхронизации по одному кабинету.
+#[derive(Debug, Default)]
+pub struct SyncResult {
+    /// Отправлений со сменой статуса в ответе API
+    pub fetched: usize,
+    pub updated: usize,
+    /// Статус в базе уже актуален (перекрытие окна, повторный запуск)
+    pub unchanged: usize,
+    /// Отправление ещё не загружено импортом u502 — пропускается
+    pub not_imported: usize,
+    pub errors: Vec<String>,
+}
+
+fn delivered_at(posting: &OzonPosting) -> Option<DateTime<Utc>> {
+    posting
+        .delivering_date
+        .as_ref()
+        .or(posting.delivered_at.as_ref())
+        .and_then(|s| {
+            DateTime::parse_from_rfc3339(s)
+                .map(|dt| dt.with_timezone(&Utc))
+                .ok()
+        })
+}
+
+/// Переносит статус из ответа API в состояние документа; `true` — состояние изменилось.
+/// Дата доставки, которой нет в ответе, не затирается.
+fn apply_status(state: &mut OzonFbsPostingState, posting: &OzonPosting) -> bool {
+    let status_norm = normalize_ozon_status(&posting.status);
+    let delivered_at = delivered_at(posting).or(state.delivered_at);
+    let changed = state.status_raw != posting.status
+        || state.status_norm != status_norm
+        || state.substatus_raw != posting.substatus
+        || state.delivered_at != delivered_at;
+    if changed {
+        state.status_raw = posting.status.clone();
+        state.status_norm = status_norm;
+        state.substatus_raw = posting.substatus.clone();
+        state.delivered_at = delivered_at;
+    }
+    changed
+}
+
+/// Обновляет статусы загруженных FBS отправлений кабинета по сменам статуса в окне.
+/// Изменённый документ сохраняется через `store_document_with_raw` — вместе с
+/// перепроведением, поэтому проекции следуют за новым статусом.
+pub async fn sync_statuses(
+    connection: &ConnectionMP,
+    window: SyncWindow,
+    session_id: Option<&str>,
+) -> Result<SyncResult> {
+    let client = OzonApiClient::new();
+    let connection_id = connection.base.id.as_string();
+    let synced_at = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    let mut result = SyncResult::default();
+    let mut offset = 0;
+
+    loop {
+        let page = client
+            .fetch_fbs_postings_status_changed(
+                connection,
+                window.created_since,
+                window.changed_from,
+                window.changed_to,
+                PAGE_LIMIT,
+                offset,
+            )
+            .await?;
+        let has_next = page.result.has_next;
+        let postings = page.result.postings;
+        result.fetched += postings.len();
+
+        let numbers: Vec<String> = postings.iter().map(|p| p.posting_number.clone()).collect();
+        let mut documents: HashMap<String, OzonFbsPosting> =
+            a010_ozon_fbs_posting::repository::get_by_document_nos(&numbers)
+                .await?
+                .into_iter()
+                .map(|d| (d.header.document_no.clone(), d))
+                .collect();
+
+        for posting in &postings {
+            let Some(mut document) = documents.remove(&posting.posting_number) else {
+                result.not_imported += 1;
+                continue;
+            };
+            let old_status = document.state.status_raw.clone();
+            let old_substatus = document.state.substatus_raw.clone();
+            if !apply_status(&mut document.state, posting) {
+                result.unchanged += 1;
+                continue;
+            }
+            document.source_meta.fetched_at = Utc::now();
+            document.source_meta.document_version += 1;
+            let document_ref = document.base.id.as_string();
+
+            let stored = async {
+                let raw_json = serde_json::to_string(posting)?;
+                a010_ozon_fbs_posting::service::store_document_with_raw(document, &raw_json)
+                    .await?;
+                log_repository::insert(log_repository::Model {
+                    id: Uuid::new_v4().to_string(),
+                    connection_id: connection_id.clone(),
+                    document_ref,
+                    document_no: posting.posting_number.clone(),
+                    old_status,
+                    new_status: posting.status.clone(),
+                    old_substatus,
+                    new_substatus: posting.substatus.clone(),
+                    synced_at: synced_at.clone(),
+                    session_id: session_id.map(str::to_string),
+                })
+                .await
+            }
+            .await;
+            match stored {
+                Ok(()) => result.updated += 1,
+                Err(e) => result
+                    .errors
+                    .push(format!("{}: {}", posting.posting_number, e)),
+            }
+        }
+
+        if !has_next || postings.is_empty() {
+            break;
+        }
+        offset += PAGE_LIMIT;
+    }
+
+    tracing::info!(
+        "u510 Ozon FBS status sync ({}): fetched {}, updated {}, unchanged {}, not imported {}, errors {}",
+        connection_id,
+        result.fetched,
+        result.updated,
+        result.unchanged,
+        result.not_imported,
+        result.errors.len()
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(status: &str, delivered_at: Option<&str>) -> OzonFbsPostingState {
+        OzonFbsPostingState {
+            status_raw: status.to_string(),
+            status_norm: normalize_ozon_status(status),
+            substatus_raw: None,
+            delivered_at: delivered_at.map(|s| s.parse().unwrap()),
+            updated_at_source: None,
+        }
+    }
+
+    fn posting(status: &str, delivering_date: Option<&str>) -> OzonPosting {
+        OzonPosting {
+            posting_number: "0123456789-0001-1".to_string(),
+            status: status.to_string(),
+            substatus: None,
+            created_at: None,
+            in_process_at: None,
+            delivering_date: delivering_date.map(str::to_string),
+            delivered_at: None,
+            products: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn status_change_updates_state() {
+        let mut current = state("delivering", None);
+        assert!(apply_status(
+            &mut current,
+            &posting("delivered", Some("2026-03-05T10:00:00Z"))
+        ));
+        assert_eq!(current.status_raw, "delivered");
+        assert_eq!(current.status_norm, "DELIVERED");
+        assert_eq!(
+            current.delivered_at,
+            Some("2026-03-05T10:00:00Z".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn same_status_is_unchanged_and_keeps_delivery_date() {
+        let mut current = state("delivered", Some("2026-03-05T10:00:00Z"));
+        assert!(!apply_status(&mut current, &posting("delivered", None)));
+        assert_eq!(
+            current.delivered_at,
+            Some("2026-03-05T10:00:00Z".parse().unwrap())
+        );
+    }
+}
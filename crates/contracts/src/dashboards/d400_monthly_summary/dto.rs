@@ -1,3 +1,4 @@
+use crate::system::data_freshness::DataFreshnessDto;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -17,6 +18,9 @@ pub struct MonthlySummaryResponse {
     pub rows: Vec<IndicatorRow>,
     /// List of marketplace codes (e.g., ["WB", "OZON", "YM"])
     pub marketplaces: Vec<String>,
+    /// Last import time of the underlying sources ("data as of" badge)
+    #[serde(default)]
+    pub freshness: Vec<DataFreshnessDto>,
 }
 
 /// Single indicator row in the dashboard
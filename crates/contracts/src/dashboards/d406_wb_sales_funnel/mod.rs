@@ -7,6 +7,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::domain::a007_marketplace_product::aggregate::AbcXyzLabelDto;
+
 pub use crate::projections::p916_mp_sales_funnel_turnovers::dto::FunnelDateAxis;
 
 /// Канал трафика для фильтра воронки. Ответ всегда содержит и total, и `paid_*`;
@@ -45,6 +47,11 @@ pub struct WbSalesFunnelRequest {
     /// Канал трафика (эхо; фактический выбор применяется на клиенте).
     #[serde(default)]
     pub channel: FunnelChannel,
+    /// Класс ABC/XYZ последнего рассчитанного квартала: `A`/`B`/`C`, `X`/`Y`/`Z`.
+    #[serde(default)]
+    pub abc_class: Option<String>,
+    #[serde(default)]
+    pub xyz_class: Option<String>,
 }
 
 /// Производные конверсии/доли воронки (проценты 0..100). None — знаменатель = 0.
@@ -157,6 +164,9 @@ pub struct WbSalesFunnelRow {
     /// Наименование товара (a004.description) — для тултипа; без склейки с артикулом.
     pub product_name: Option<String>,
     pub brand: Option<String>,
+    /// ABC/XYZ-класс товара (a007) за последний рассчитанный квартал.
+    #[serde(default)]
+    pub abc_xyz: Option<AbcXyzLabelDto>,
     pub metrics: WbSalesFunnelMetrics,
     pub conversions: WbSalesFunnelConversions,
 }
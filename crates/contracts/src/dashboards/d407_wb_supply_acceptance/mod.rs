@@ -0,0 +1,94 @@
+//! D407 — расхождения приёмки поставок WB.
+//!
+//! Для каждой поставки a029 сравнивается, сколько единиц мы отгрузили
+//! (строки `supply_orders`), с тем, сколько WB числит по этой поставке:
+//! заказы a015 с `income_id` поставки («принято») и их продажи a012
+//! («продано»). Недостача выше порога помечается для претензии.
+
+use serde::{Deserialize, Serialize};
+
+/// Порог недостачи по умолчанию: помечается любая недостача.
+pub const DEFAULT_SHORTFALL_THRESHOLD: i64 = 0;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WbSupplyAcceptanceRequest {
+    /// Период по дате создания поставки в WB (`YYYY-MM-DD`).
+    #[serde(default)]
+    pub date_from: Option<String>,
+    #[serde(default)]
+    pub date_to: Option<String>,
+    #[serde(default)]
+    pub connection_mp_ref: Option<String>,
+    /// Недостача (шт.), свыше которой строка помечается.
+    #[serde(default)]
+    pub threshold: Option<i64>,
+}
+
+/// Строка отчёта: поставка × товар (nmId).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WbSupplyAcceptanceRow {
+    /// id документа a029.
+    pub supply_ref: String,
+    /// ID поставки WB, напр. `"WB-GI-12345678"`.
+    pub supply_id: String,
+    pub supply_name: Option<String>,
+    pub created_at_wb: Option<String>,
+    pub connection_mp_ref: String,
+    pub nm_id: Option<i64>,
+    pub article: String,
+    /// Отгружено нами (строк в поставке).
+    pub shipped_qty: i64,
+    /// Числится у WB по поставке (заказы a015 с `income_id` поставки).
+    pub accepted_qty: i64,
+    /// Из принятых — продано (есть продажа a012).
+    pub sold_qty: i64,
+    pub shortfall: i64,
+    pub is_flagged: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WbSupplyAcceptanceTotals {
+    pub supplies: i64,
+    pub shipped_qty: i64,
+    pub accepted_qty: i64,
+    pub sold_qty: i64,
+    pub shortfall: i64,
+    pub flagged_rows: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WbSupplyAcceptanceResponse {
+    pub filters: WbSupplyAcceptanceRequest,
+    pub threshold: i64,
+    pub totals: WbSupplyAcceptanceTotals,
+    pub rows: Vec<WbSupplyAcceptanceRow>,
+}
+
+/// Недостача: сколько отгруженных единиц WB не числит. Излишек не считается.
+pub fn shortfall(shipped_qty: i64, accepted_qty: i64) -> i64 {
+    (shipped_qty - accepted_qty).max(0)
+}
+
+/// Строка попадает в претензию, если недостача больше порога.
+pub fn is_flagged(shortfall: i64, threshold: i64) -> bool {
+    shortfall > 0 && shortfall > threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surplus_is_not_a_shortfall() {
+        assert_eq!(shortfall(10, 7), 3);
+        assert_eq!(shortfall(5, 8), 0);
+    }
+
+    #[test]
+    fn flags_only_shortfall_above_threshold() {
+        assert!(is_flagged(1, DEFAULT_SHORTFALL_THRESHOLD));
+        assert!(!is_flagged(0, DEFAULT_SHORTFALL_THRESHOLD));
+        assert!(!is_flagged(2, 2));
+        assert!(is_flagged(3, 2));
+    }
+}
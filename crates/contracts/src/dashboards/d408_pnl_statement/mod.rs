@@ -0,0 +1,267 @@
+//! D408 — помесячный отчёт о прибылях и убытках (P&L) по организациям.
+//!
+//! Отчёт собирается из проводок GL (`sys_general_ledger`): каждая проводка
+//! попадает в строку P&L по группе отчёта своего оборота (`ReportGroup`), а
+//! её влияние на результат определяется счетами: кредит счёта P&L — доход,
+//! дебет — расход. Организация берётся из подключения МП (a006).
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::analytics::turnover::ReportGroup;
+
+/// Слой GL по умолчанию — финансовые отчёты маркетплейсов (p903/p907).
+pub const DEFAULT_LAYER: &str = "fina";
+/// Реклама начисляется только в операционном слое (a026).
+pub const ADVERTISING_LAYER: &str = "oper";
+/// Себестоимость списывается только в производственном слое (p909).
+pub const COST_LAYER: &str = "prod";
+/// Измерение GL-детализации для перехода к строкам-источникам.
+pub const DRILLDOWN_GROUP_BY: &str = "registrator_ref";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PnlStatementRequest {
+    /// Период по дате проводки (`YYYY-MM-DD`).
+    pub date_from: String,
+    pub date_to: String,
+    #[serde(default)]
+    pub organization_ref: Option<String>,
+    /// Явный слой GL для всех строк. `None` — слой по строке, см. [`source_layer`].
+    #[serde(default)]
+    pub layer: Option<String>,
+}
+
+/// Строка P&L в порядке вывода.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PnlLine {
+    Revenue,
+    Returns,
+    Commission,
+    Acquiring,
+    Logistics,
+    Storage,
+    Advertising,
+    Penalty,
+    Cost,
+    Other,
+}
+
+impl PnlLine {
+    pub const ALL: [PnlLine; 10] = [
+        PnlLine::Revenue,
+        PnlLine::Returns,
+        PnlLine::Commission,
+        PnlLine::Acquiring,
+        PnlLine::Logistics,
+        PnlLine::Storage,
+        PnlLine::Advertising,
+        PnlLine::Penalty,
+        PnlLine::Cost,
+        PnlLine::Other,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PnlLine::Revenue => "Выручка",
+            PnlLine::Returns => "Возвраты",
+            PnlLine::Commission => "Комиссия МП",
+            PnlLine::Acquiring => "Эквайринг",
+            PnlLine::Logistics => "Логистика",
+            PnlLine::Storage => "Хранение",
+            PnlLine::Advertising => "Реклама",
+            PnlLine::Penalty => "Штрафы",
+            PnlLine::Cost => "Себестоимость",
+            PnlLine::Other => "Прочие доходы и расходы",
+        }
+    }
+
+    /// Строка P&L для группы отчёта оборота. Корректировки, выплаты и
+    /// прочее, если затрагивают счета P&L, попадают в «Прочие».
+    pub fn from_report_group(group: ReportGroup) -> Self {
+        match group {
+            ReportGroup::Revenue => PnlLine::Revenue,
+            ReportGroup::Returns => PnlLine::Returns,
+            ReportGroup::Commission => PnlLine::Commission,
+            ReportGroup::Acquiring => PnlLine::Acquiring,
+            ReportGroup::Logistics => PnlLine::Logistics,
+            ReportGroup::Storage => PnlLine::Storage,
+            ReportGroup::Advertising => PnlLine::Advertising,
+            ReportGroup::Penalty => PnlLine::Penalty,
+            ReportGroup::Cost => PnlLine::Cost,
+            _ => PnlLine::Other,
+        }
+    }
+}
+
+/// Слой GL, из которого берётся строка: явный слой запроса либо слой,
+/// где эта статья реально проводится.
+pub fn source_layer(line: PnlLine, layer: Option<&str>) -> &str {
+    if let Some(layer) = layer.map(str::trim).filter(|value| !value.is_empty()) {
+        return layer;
+    }
+    match line {
+        PnlLine::Advertising => ADVERTISING_LAYER,
+        PnlLine::Cost => COST_LAYER,
+        _ => DEFAULT_LAYER,
+    }
+}
+
+/// Влияние проводки на финансовый результат: кредит счёта P&L увеличивает
+/// результат, дебет — уменьшает. Проводка между двумя счетами P&L даёт ноль.
+pub fn pnl_effect(amount: f64, debit_is_pnl: bool, credit_is_pnl: bool) -> f64 {
+    let mut effect = 0.0;
+    if credit_is_pnl {
+        effect += amount;
+    }
+    if debit_is_pnl {
+        effect -= amount;
+    }
+    effect
+}
+
+/// Месяцы периода (`YYYY-MM`) от `date_from` до `date_to` включительно.
+pub fn months_in_period(date_from: &str, date_to: &str) -> Vec<String> {
+    let (Some(mut current), Some(last)) = (parse_month(date_from), parse_month(date_to)) else {
+        return Vec::new();
+    };
+    let mut months = Vec::new();
+    while current <= last {
+        months.push(format!("{:04}-{:02}", current.0, current.1));
+        current = if current.1 == 12 {
+            (current.0 + 1, 1)
+        } else {
+            (current.0, current.1 + 1)
+        };
+    }
+    months
+}
+
+/// Границы месяца `YYYY-MM`, обрезанные периодом отчёта, — период GL-детализации ячейки.
+pub fn month_bounds(month: &str, date_from: &str, date_to: &str) -> (String, String) {
+    let Some((year, month_no)) = parse_month(month) else {
+        return (date_from.to_string(), date_to.to_string());
+    };
+    let first = format!("{year:04}-{month_no:02}-01");
+    let last = format!(
+        "{year:04}-{month_no:02}-{:02}",
+        days_in_month(year, month_no)
+    );
+    let from = if date_from > first.as_str() {
+        date_from.to_string()
+    } else {
+        first
+    };
+    let to = if !date_to.is_empty() && date_to < last.as_str() {
+        date_to.to_string()
+    } else {
+        last
+    };
+    (from, to)
+}
+
+fn parse_month(value: &str) -> Option<(i32, u32)> {
+    let year = value.get(0..4)?.parse().ok()?;
+    let month = value.get(5..7)?.parse().ok()?;
+    (1..=12).contains(&month).then_some((year, month))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Оборот внутри строки P&L — единица детализации.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnlTurnoverRow {
+    pub turnover_code: String,
+    pub turnover_name: String,
+    pub layer: String,
+    /// Значения по месяцам в порядке `PnlStatementResponse::months`.
+    pub values: Vec<f64>,
+    pub total: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnlLineRow {
+    pub line: PnlLine,
+    pub label: String,
+    pub values: Vec<f64>,
+    pub total: f64,
+    pub turnovers: Vec<PnlTurnoverRow>,
+}
+
+/// P&L одной организации.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnlOrganizationSection {
+    pub organization_ref: String,
+    pub organization_name: String,
+    /// Подключения МП организации — фильтр для GL-детализации.
+    pub connection_mp_refs: Vec<String>,
+    pub lines: Vec<PnlLineRow>,
+    /// Финансовый результат по месяцам (сумма строк).
+    pub result: Vec<f64>,
+    pub result_total: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnlStatementResponse {
+    pub filters: PnlStatementRequest,
+    /// Месяцы периода (`YYYY-MM`).
+    pub months: Vec<String>,
+    pub organizations: Vec<PnlOrganizationSection>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn effect_follows_pnl_account_side() {
+        // Дт7609 Кт9001 — выручка.
+        assert_eq!(pnl_effect(100.0, false, true), 100.0);
+        // Дт4401 Кт7609 — комиссия.
+        assert_eq!(pnl_effect(15.0, true, false), -15.0);
+        // Сторно выручки хранится отрицательной суммой на тех же счетах.
+        assert_eq!(pnl_effect(-40.0, false, true), -40.0);
+        assert_eq!(pnl_effect(10.0, true, true), 0.0);
+        assert_eq!(pnl_effect(10.0, false, false), 0.0);
+    }
+
+    #[test]
+    fn months_span_year_boundary() {
+        assert_eq!(
+            months_in_period("2025-11-15", "2026-02-03"),
+            vec!["2025-11", "2025-12", "2026-01", "2026-02"]
+        );
+        assert!(months_in_period("2026-03-01", "2026-02-01").is_empty());
+        assert!(months_in_period("", "2026-02-01").is_empty());
+    }
+
+    #[test]
+    fn month_bounds_are_clamped_to_period() {
+        assert_eq!(
+            month_bounds("2025-11", "2025-11-15", "2026-02-03"),
+            ("2025-11-15".to_string(), "2025-11-30".to_string())
+        );
+        assert_eq!(
+            month_bounds("2024-02", "2024-01-01", "2024-12-31"),
+            ("2024-02-01".to_string(), "2024-02-29".to_string())
+        );
+        assert_eq!(
+            month_bounds("2026-02", "2025-11-15", "2026-02-03"),
+            ("2026-02-01".to_string(), "2026-02-03".to_string())
+        );
+    }
+
+    #[test]
+    fn explicit_layer_overrides_line_layers() {
+        assert_eq!(source_layer(PnlLine::Revenue, None), DEFAULT_LAYER);
+        assert_eq!(source_layer(PnlLine::Advertising, None), ADVERTISING_LAYER);
+        assert_eq!(source_layer(PnlLine::Cost, Some(" ")), COST_LAYER);
+        assert_eq!(source_layer(PnlLine::Cost, Some("oper")), "oper");
+    }
+}
@@ -0,0 +1,153 @@
+//! D409 — сценарный калькулятор маржи («что если») по выбранным SKU WB.
+//!
+//! Фактическая экономика единицы берётся за период из финансового отчёта
+//! WB (p903: цена, комиссия, эквайринг, логистика), дилерской цены заказов
+//! a015 (себестоимость) и хранения, распределённого на SKU (p918). Допущения
+//! пользователя подменяют отдельные составляющие, маржа считается формулами
+//! `shared::analytics::margin` — теми же, что и `margin_pro` при проведении заказов.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::a007_marketplace_product::aggregate::AbcXyzLabelDto;
+use crate::shared::analytics::margin::UnitEconomics;
+
+/// Сколько SKU принимается в одном сценарии.
+pub const MAX_SCENARIO_ITEMS: usize = 200;
+
+/// Допущения сценария. `None` — составляющая остаётся фактической.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct MarginAssumptions {
+    /// Изменение цены, % (например, `5` — поднять на 5 %). Не действует на
+    /// SKU, для которых цена задана явно.
+    #[serde(default)]
+    pub price_change_percent: Option<f64>,
+    #[serde(default)]
+    pub commission_percent: Option<f64>,
+    #[serde(default)]
+    pub acquiring_percent: Option<f64>,
+    #[serde(default)]
+    pub logistics_per_unit: Option<f64>,
+    /// Изменение себестоимости, %.
+    #[serde(default)]
+    pub cost_change_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarginScenarioItem {
+    pub nm_id: i64,
+    /// Цена сценария для этого SKU, заменяет `price_change_percent`.
+    #[serde(default)]
+    pub price: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MarginScenarioRequest {
+    /// Период фактических данных (`YYYY-MM-DD`).
+    pub date_from: String,
+    pub date_to: String,
+    #[serde(default)]
+    pub connection_mp_ref: Option<String>,
+    pub items: Vec<MarginScenarioItem>,
+    #[serde(default)]
+    pub assumptions: MarginAssumptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarginScenarioRow {
+    pub nm_id: i64,
+    pub article: String,
+    /// Продано за период (шт.) — объём для оценки прибыли.
+    pub sold_qty: f64,
+    /// Средняя `margin_pro` заказов a015 за период.
+    pub orders_margin_pro: Option<f64>,
+    /// ABC/XYZ-класс товара за последний рассчитанный квартал.
+    #[serde(default)]
+    pub abc_xyz: Option<AbcXyzLabelDto>,
+    pub actual: UnitEconomics,
+    pub scenario: UnitEconomics,
+    pub actual_profit_per_unit: f64,
+    pub scenario_profit_per_unit: f64,
+    pub actual_markup_percent: Option<f64>,
+    pub scenario_markup_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MarginScenarioTotals {
+    pub sold_qty: f64,
+    /// Прибыль при фактическом объёме продаж.
+    pub actual_profit: f64,
+    pub scenario_profit: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarginScenarioResponse {
+    pub rows: Vec<MarginScenarioRow>,
+    pub totals: MarginScenarioTotals,
+}
+
+/// Экономика единицы в сценарии: факт с применёнными допущениями.
+pub fn apply_assumptions(
+    actual: &UnitEconomics,
+    price_override: Option<f64>,
+    assumptions: &MarginAssumptions,
+) -> UnitEconomics {
+    let scaled = |value: f64, change: Option<f64>| value * (100.0 + change.unwrap_or(0.0)) / 100.0;
+    UnitEconomics {
+        price: price_override
+            .unwrap_or_else(|| scaled(actual.price, assumptions.price_change_percent)),
+        commission_percent: assumptions
+            .commission_percent
+            .unwrap_or(actual.commission_percent),
+        acquiring_percent: assumptions
+            .acquiring_percent
+            .unwrap_or(actual.acquiring_percent),
+        logistics_per_unit: assumptions
+            .logistics_per_unit
+            .unwrap_or(actual.logistics_per_unit),
+        cost_per_unit: scaled(actual.cost_per_unit, assumptions.cost_change_percent),
+        storage_per_unit: actual.storage_per_unit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actual() -> UnitEconomics {
+        UnitEconomics {
+            price: 1000.0,
+            commission_percent: 20.0,
+            acquiring_percent: 1.5,
+            logistics_per_unit: 70.0,
+            cost_per_unit: 400.0,
+            storage_per_unit: 12.0,
+        }
+    }
+
+    #[test]
+    fn empty_assumptions_keep_actuals() {
+        let actual = actual();
+        assert_eq!(
+            apply_assumptions(&actual, None, &MarginAssumptions::default()),
+            actual
+        );
+    }
+
+    #[test]
+    fn explicit_price_wins_over_price_change() {
+        let assumptions = MarginAssumptions {
+            price_change_percent: Some(10.0),
+            cost_change_percent: Some(-25.0),
+            commission_percent: Some(18.0),
+            ..Default::default()
+        };
+        let scenario = apply_assumptions(&actual(), None, &assumptions);
+        assert_eq!(scenario.price, 1100.0);
+        assert_eq!(scenario.cost_per_unit, 300.0);
+        assert_eq!(scenario.commission_percent, 18.0);
+        assert_eq!(scenario.logistics_per_unit, 70.0);
+
+        let scenario = apply_assumptions(&actual(), Some(950.0), &assumptions);
+        assert_eq!(scenario.price, 950.0);
+    }
+}
@@ -0,0 +1,153 @@
+//! D410 — когорты запусков SKU: окупаются ли новые товары.
+//!
+//! Товар маркетплейса (a007) относится к когорте месяца своей первой продажи в
+//! регистре p900 (с учётом архива). Для каждой когорты по месяцам жизни
+//! (M0 — месяц запуска, M1 — следующий, …) считаются выручка и валовая маржа
+//! (выручка минус себестоимость p900) и их накопительные итоги. Если задать
+//! вложения в запуск одного SKU, когорта получает месяц окупаемости — первый, в
+//! котором накопленная маржа на SKU покрыла вложения.
+
+use serde::{Deserialize, Serialize};
+
+/// Горизонт по умолчанию — год жизни когорты.
+pub const DEFAULT_HORIZON_MONTHS: u32 = 12;
+pub const MAX_HORIZON_MONTHS: u32 = 36;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SkuLaunchCohortsRequest {
+    /// Период месяцев запуска (`YYYY-MM-DD`, берётся месяц).
+    pub date_from: String,
+    pub date_to: String,
+    #[serde(default)]
+    pub connection_mp_ref: Option<String>,
+    /// Сколько месяцев жизни показывать, по умолчанию [`DEFAULT_HORIZON_MONTHS`].
+    #[serde(default)]
+    pub horizon_months: Option<u32>,
+    /// Вложения в запуск одного SKU, ₽ — порог окупаемости.
+    #[serde(default)]
+    pub launch_cost_per_sku: Option<f64>,
+}
+
+impl SkuLaunchCohortsRequest {
+    pub fn horizon(&self) -> u32 {
+        self.horizon_months
+            .unwrap_or(DEFAULT_HORIZON_MONTHS)
+            .clamp(1, MAX_HORIZON_MONTHS)
+    }
+}
+
+/// Месяц жизни когорты.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CohortMonthCell {
+    /// Номер месяца жизни: 0 — месяц запуска.
+    pub offset: u32,
+    /// Календарный месяц `YYYY-MM`.
+    pub month: String,
+    /// SKU когорты с движением в этом месяце.
+    pub active_skus: i64,
+    pub revenue: f64,
+    pub margin: f64,
+    pub cumulative_revenue: f64,
+    pub cumulative_margin: f64,
+    /// Накопленная маржа на один SKU когорты.
+    pub cumulative_margin_per_sku: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkuLaunchCohort {
+    /// Месяц запуска `YYYY-MM`.
+    pub launch_month: String,
+    pub sku_count: i64,
+    /// Месяцы жизни от M0 до горизонта или текущего месяца.
+    pub cells: Vec<CohortMonthCell>,
+    /// Первый месяц жизни, в котором когорта окупила вложения.
+    pub payback_offset: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkuLaunchCohortsResponse {
+    pub filters: SkuLaunchCohortsRequest,
+    pub horizon_months: u32,
+    pub cohorts: Vec<SkuLaunchCohort>,
+}
+
+fn parse_month(value: &str) -> Option<(i32, u32)> {
+    let year = value.get(0..4)?.parse().ok()?;
+    let month = value.get(5..7)?.parse().ok()?;
+    (1..=12).contains(&month).then_some((year, month))
+}
+
+/// Сколько месяцев от `from` до `to` (`YYYY-MM…`); None, если `to` раньше.
+pub fn month_offset(from: &str, to: &str) -> Option<u32> {
+    let (from_year, from_month) = parse_month(from)?;
+    let (to_year, to_month) = parse_month(to)?;
+    let diff = (to_year * 12 + to_month as i32) - (from_year * 12 + from_month as i32);
+    u32::try_from(diff).ok()
+}
+
+/// Месяц `YYYY-MM`, отстоящий от `month` на `offset` месяцев вперёд.
+pub fn add_months(month: &str, offset: u32) -> Option<String> {
+    let (year, month_no) = parse_month(month)?;
+    let index = year * 12 + (month_no as i32 - 1) + offset as i32;
+    Some(format!("{:04}-{:02}", index / 12, index % 12 + 1))
+}
+
+/// Проставить накопительные итоги ячеек, идущих по порядку месяцев жизни.
+pub fn accumulate(cells: &mut [CohortMonthCell], sku_count: i64) {
+    let mut revenue = 0.0;
+    let mut margin = 0.0;
+    for cell in cells {
+        revenue += cell.revenue;
+        margin += cell.margin;
+        cell.cumulative_revenue = revenue;
+        cell.cumulative_margin = margin;
+        cell.cumulative_margin_per_sku = if sku_count > 0 {
+            margin / sku_count as f64
+        } else {
+            0.0
+        };
+    }
+}
+
+/// Первый месяц жизни, когда накопленная маржа на SKU покрыла вложения в запуск.
+pub fn payback_offset(cells: &[CohortMonthCell], launch_cost_per_sku: Option<f64>) -> Option<u32> {
+    let cost = launch_cost_per_sku.filter(|cost| *cost > 0.0)?;
+    cells
+        .iter()
+        .find(|cell| cell.cumulative_margin_per_sku >= cost)
+        .map(|cell| cell.offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(offset: u32, margin: f64) -> CohortMonthCell {
+        CohortMonthCell {
+            offset,
+            margin,
+            revenue: margin * 4.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn month_arithmetic_crosses_year() {
+        assert_eq!(month_offset("2025-11", "2026-02-15"), Some(3));
+        assert_eq!(month_offset("2026-02", "2026-01"), None);
+        assert_eq!(add_months("2025-11", 3).as_deref(), Some("2026-02"));
+        assert_eq!(add_months("2025-12", 0).as_deref(), Some("2025-12"));
+    }
+
+    #[test]
+    fn payback_when_cumulative_margin_per_sku_covers_cost() {
+        let mut cells = vec![cell(0, 100.0), cell(1, 300.0), cell(2, 400.0)];
+        accumulate(&mut cells, 2);
+        assert_eq!(cells[1].cumulative_revenue, 1600.0);
+        assert_eq!(cells[2].cumulative_margin_per_sku, 400.0);
+
+        assert_eq!(payback_offset(&cells, Some(200.0)), Some(1));
+        assert_eq!(payback_offset(&cells, Some(500.0)), None);
+        assert_eq!(payback_offset(&cells, None), None);
+    }
+}
@@ -0,0 +1,161 @@
+//! D412 — прогноз спроса против факта.
+//!
+//! Прогноз строит task029 раз в неделю: по каждому товару a007 — недельные продажи
+//! в штуках на горизонт вперёд (экспоненциальное сглаживание с помесячной
+//! сезонностью, у коротких рядов — скользящее среднее). Каждый запуск сохраняется
+//! отдельно, поэтому факт недели сравнивается с прогнозом, сделанным за
+//! `lead_weeks` недель до неё, — так видна реальная точность планирования.
+
+use serde::{Deserialize, Serialize};
+
+/// Недель горизонта, которые показываются в сводке по товарам.
+pub const PRODUCT_SUMMARY_WEEKS: usize = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DemandForecastRequest {
+    /// Период недель факта (`YYYY-MM-DD`, неделя — с понедельника).
+    pub date_from: String,
+    pub date_to: String,
+    #[serde(default)]
+    pub connection_mp_ref: Option<String>,
+    /// Товар a007; пусто — сумма по всем товарам.
+    #[serde(default)]
+    pub marketplace_product_ref: Option<String>,
+    /// За сколько недель до факта сделан сравниваемый прогноз (0 — в начале той же недели).
+    #[serde(default)]
+    pub lead_weeks: Option<u32>,
+}
+
+/// Метод прогноза товара.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForecastMethod {
+    /// Среднее по короткой истории.
+    MovingAverage,
+    /// Экспоненциальное сглаживание без сезонности (история меньше года).
+    ExponentialSmoothing,
+    /// Экспоненциальное сглаживание с помесячными сезонными коэффициентами.
+    SeasonalSmoothing,
+}
+
+impl ForecastMethod {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MovingAverage => "moving_average",
+            Self::ExponentialSmoothing => "exponential_smoothing",
+            Self::SeasonalSmoothing => "seasonal_smoothing",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "moving_average" => Some(Self::MovingAverage),
+            "exponential_smoothing" => Some(Self::ExponentialSmoothing),
+            "seasonal_smoothing" => Some(Self::SeasonalSmoothing),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::MovingAverage => "Скользящее среднее",
+            Self::ExponentialSmoothing => "Экспоненциальное сглаживание",
+            Self::SeasonalSmoothing => "Сглаживание с сезонностью",
+        }
+    }
+}
+
+/// Неделя на графике.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DemandForecastPoint {
+    /// Понедельник недели `YYYY-MM-DD`.
+    pub week_start: String,
+    /// Продано за неделю, шт.; None — неделя ещё не наступила или не закончилась.
+    pub actual_qty: Option<f64>,
+    pub forecast_qty: Option<f64>,
+}
+
+/// Ошибка прогноза по неделям, где есть и прогноз, и факт.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ForecastErrorMetrics {
+    pub weeks: usize,
+    /// Средняя абсолютная ошибка, шт. в неделю.
+    pub mae: f64,
+    /// Взвешенная абсолютная ошибка, % от факта; None — факта не было.
+    pub wape: Option<f64>,
+    /// Смещение (прогноз − факт) в % от факта: плюс — прогноз завышает.
+    pub bias: Option<f64>,
+}
+
+/// Метрики ошибки по парам `(прогноз, факт)`.
+pub fn error_metrics(pairs: &[(f64, f64)]) -> ForecastErrorMetrics {
+    if pairs.is_empty() {
+        return ForecastErrorMetrics::default();
+    }
+    let abs_error: f64 = pairs
+        .iter()
+        .map(|(forecast, actual)| (forecast - actual).abs())
+        .sum();
+    let error: f64 = pairs
+        .iter()
+        .map(|(forecast, actual)| forecast - actual)
+        .sum();
+    let actual: f64 = pairs.iter().map(|(_, actual)| actual).sum();
+    let share = |value: f64| (actual > 0.0).then(|| value / actual * 100.0);
+    ForecastErrorMetrics {
+        weeks: pairs.len(),
+        mae: abs_error / pairs.len() as f64,
+        wape: share(abs_error),
+        bias: share(error),
+    }
+}
+
+/// Товар в сводке: прогноз на ближайшие недели и точность за период.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DemandForecastProductRow {
+    pub marketplace_product_ref: String,
+    pub article: String,
+    pub product_name: String,
+    pub method: Option<ForecastMethod>,
+    /// Сумма последнего прогноза на [`PRODUCT_SUMMARY_WEEKS`] недель вперёд, шт.
+    pub forecast_next_weeks: f64,
+    /// Факт за период, шт.
+    pub actual_qty: f64,
+    pub metrics: ForecastErrorMetrics,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DemandForecastResponse {
+    pub filters: DemandForecastRequest,
+    pub lead_weeks: u32,
+    /// Понедельник недели последнего расчёта прогноза.
+    pub latest_run: Option<String>,
+    /// Факт и прогноз с выбранным упреждением по неделям периода.
+    pub history: Vec<DemandForecastPoint>,
+    /// Последний прогноз на будущие недели.
+    pub outlook: Vec<DemandForecastPoint>,
+    pub metrics: ForecastErrorMetrics,
+    pub products: Vec<DemandForecastProductRow>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_metrics_weight_errors_by_actual_volume() {
+        let metrics = error_metrics(&[(12.0, 10.0), (5.0, 10.0), (20.0, 20.0)]);
+        assert_eq!(metrics.weeks, 3);
+        assert!((metrics.mae - 7.0 / 3.0).abs() < 1e-9);
+        assert_eq!(metrics.wape, Some(17.5));
+        assert_eq!(metrics.bias, Some(-7.5));
+    }
+
+    #[test]
+    fn error_metrics_without_actual_sales() {
+        let metrics = error_metrics(&[(3.0, 0.0)]);
+        assert_eq!(metrics.mae, 3.0);
+        assert_eq!(metrics.wape, None);
+        assert_eq!(error_metrics(&[]), ForecastErrorMetrics::default());
+    }
+}
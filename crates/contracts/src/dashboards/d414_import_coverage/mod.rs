@@ -0,0 +1,212 @@
+//! D414 — покрытие импорта по дням.
+//!
+//! task032 раз в день спрашивает у маркетплейсов, сколько документов и на какую
+//! сумму у них числится за каждый из последних дней (сводные ответы API), и
+//! сохраняет эти «заявленные» цифры. Отчёт сравнивает их с тем, что реально
+//! лежит в агрегатах, — пропущенный или недогруженный день виден сразу, а не
+//! на закрытии месяца. Импортированная сторона считается в момент запроса,
+//! поэтому после догрузки расхождение исчезает без повторного запуска задачи.
+
+use serde::{Deserialize, Serialize};
+
+/// Допустимое расхождение сумм, руб.: копейки округления API не считаются пропуском.
+pub const AMOUNT_TOLERANCE: f64 = 1.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ImportCoverageRequest {
+    /// Период дней (`YYYY-MM-DD`, включительно).
+    pub date_from: String,
+    pub date_to: String,
+    #[serde(default)]
+    pub connection_mp_ref: Option<String>,
+    /// Только дни с расхождениями.
+    #[serde(default)]
+    pub problems_only: Option<bool>,
+}
+
+/// Что сверяется: пара «сводка API маркетплейса» ↔ агрегат.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverageSource {
+    /// WB Statistics API `/api/v1/supplier/sales` ↔ a012_wb_sales.
+    WbSales,
+    /// Ozon `/v3/finance/transaction/list` + `/totals` ↔ a014_ozon_transactions.
+    OzonTransactions,
+}
+
+impl CoverageSource {
+    pub fn all() -> [Self; 2] {
+        [Self::WbSales, Self::OzonTransactions]
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::WbSales => "wb_sales",
+            Self::OzonTransactions => "ozon_transactions",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::all().into_iter().find(|source| source.code() == code)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::WbSales => "WB продажи (a012)",
+            Self::OzonTransactions => "Ozon транзакции (a014)",
+        }
+    }
+
+    /// Код маркетплейса (`MarketplaceType::code()`), к кабинетам которого относится источник.
+    pub fn marketplace_code(&self) -> &'static str {
+        match self {
+            Self::WbSales => "mp-wb",
+            Self::OzonTransactions => "mp-ozon",
+        }
+    }
+}
+
+/// Итог сверки одного дня.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverageStatus {
+    Ok,
+    /// У маркетплейса документы есть, у нас — ни одного.
+    Missing,
+    /// Загружена только часть документов.
+    Partial,
+    /// У нас документов больше, чем заявлено (дубли или удалённые у МП).
+    Surplus,
+    /// Количество совпадает, суммы — нет.
+    AmountMismatch,
+    /// Сводку маркетплейса за день ещё не получали.
+    NotChecked,
+}
+
+impl CoverageStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Ok => "Сходится",
+            Self::Missing => "Не загружен",
+            Self::Partial => "Загружен частично",
+            Self::Surplus => "Лишние документы",
+            Self::AmountMismatch => "Расходится сумма",
+            Self::NotChecked => "Не проверен",
+        }
+    }
+
+    /// День требует разбора.
+    pub fn is_problem(&self) -> bool {
+        !matches!(self, Self::Ok | Self::NotChecked)
+    }
+
+    /// День загружен не полностью — данные надо догрузить.
+    pub fn is_gap(&self) -> bool {
+        matches!(self, Self::Missing | Self::Partial)
+    }
+}
+
+/// Сравнение заявленного маркетплейсом с импортированным.
+///
+/// `claimed_count = None` — сводки нет. Сумма сверяется, только если её вернул API.
+pub fn classify(
+    claimed_count: Option<i64>,
+    claimed_amount: Option<f64>,
+    imported_count: i64,
+    imported_amount: f64,
+) -> CoverageStatus {
+    let Some(claimed_count) = claimed_count else {
+        return CoverageStatus::NotChecked;
+    };
+    if claimed_count > 0 && imported_count == 0 {
+        return CoverageStatus::Missing;
+    }
+    if imported_count < claimed_count {
+        return CoverageStatus::Partial;
+    }
+    if imported_count > claimed_count {
+        return CoverageStatus::Surplus;
+    }
+    match claimed_amount {
+        Some(amount) if (amount - imported_amount).abs() > AMOUNT_TOLERANCE => {
+            CoverageStatus::AmountMismatch
+        }
+        _ => CoverageStatus::Ok,
+    }
+}
+
+/// Строка отчёта: день × кабинет × источник.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportCoverageRow {
+    /// `YYYY-MM-DD`.
+    pub day: String,
+    pub connection_mp_ref: String,
+    pub connection_name: String,
+    pub source: CoverageSource,
+    /// Сколько документов заявил маркетплейс; None — сводки нет.
+    pub claimed_count: Option<i64>,
+    pub claimed_amount: Option<f64>,
+    pub imported_count: i64,
+    pub imported_amount: f64,
+    pub status: CoverageStatus,
+    /// Когда получена сводка маркетплейса (UTC ISO8601).
+    pub checked_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportCoverageResponse {
+    pub filters: ImportCoverageRequest,
+    /// Новые дни сверху.
+    pub rows: Vec<ImportCoverageRow>,
+    /// Строк с расхождениями.
+    pub problem_rows: usize,
+    /// Дней, где хотя бы по одному кабинету данные загружены не полностью.
+    pub gap_days: usize,
+    /// Последняя полученная сводка маркетплейса.
+    pub last_checked_at: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_counts_before_amounts() {
+        assert_eq!(classify(None, None, 5, 100.0), CoverageStatus::NotChecked);
+        assert_eq!(classify(Some(3), None, 0, 0.0), CoverageStatus::Missing);
+        assert_eq!(
+            classify(Some(3), Some(300.0), 2, 200.0),
+            CoverageStatus::Partial
+        );
+        assert_eq!(
+            classify(Some(3), Some(300.0), 4, 400.0),
+            CoverageStatus::Surplus
+        );
+        assert_eq!(classify(Some(0), Some(0.0), 0, 0.0), CoverageStatus::Ok);
+    }
+
+    #[test]
+    fn classify_tolerates_rounding_in_amounts() {
+        assert_eq!(
+            classify(Some(2), Some(1000.0), 2, 999.5),
+            CoverageStatus::Ok
+        );
+        assert_eq!(
+            classify(Some(2), Some(1000.0), 2, 900.0),
+            CoverageStatus::AmountMismatch
+        );
+        // Сумму API не вернул — сверяется только количество.
+        assert_eq!(classify(Some(2), None, 2, 900.0), CoverageStatus::Ok);
+    }
+
+    #[test]
+    fn source_codes_round_trip() {
+        for source in CoverageSource::all() {
+            assert_eq!(CoverageSource::from_code(source.code()), Some(source));
+        }
+        assert!(CoverageStatus::Partial.is_gap());
+        assert!(!CoverageStatus::Surplus.is_gap());
+        assert!(CoverageStatus::Surplus.is_problem());
+        assert!(!CoverageStatus::NotChecked.is_problem());
+    }
+}
@@ -0,0 +1,144 @@
+//! D415 — P&L по SKU с сравнением с предыдущим периодом.
+//!
+//! Считается по продажам p904_sales_data: на каждую номенклатуру — выручка,
+//! комиссия МП, эквайринг, соинвест МП и чистая выплата продавцу за выбранный
+//! период и за предыдущий период той же длины. Строки p904 без номенклатуры
+//! группируются по артикулу МП. Знаки как в p904: расходы отрицательные,
+//! выплата = выручка + соинвест + комиссия + эквайринг (`-seller_out`).
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Максимум точек тренда: период делится на равные отрезки, но не мельче дня.
+pub const TREND_BUCKETS: usize = 12;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SkuPnlRequest {
+    /// Период по дате продажи (`YYYY-MM-DD`, включительно).
+    pub date_from: String,
+    pub date_to: String,
+    #[serde(default)]
+    pub connection_mp_ref: Option<String>,
+}
+
+/// Показатели P&L одного SKU (или итога) за период.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct SkuPnlMetrics {
+    /// `customer_in + customer_out` — продажи за вычетом возвратов.
+    pub revenue: f64,
+    /// `commission_out`
+    pub commission: f64,
+    /// `acquiring_out`
+    pub acquiring: f64,
+    /// `coinvest_in` — соинвест (скидка за счёт МП).
+    pub coinvest: f64,
+    /// `-seller_out` — чистая выплата продавцу.
+    pub payout: f64,
+}
+
+impl SkuPnlMetrics {
+    pub fn add(&mut self, other: &SkuPnlMetrics) {
+        self.revenue += other.revenue;
+        self.commission += other.commission;
+        self.acquiring += other.acquiring;
+        self.coinvest += other.coinvest;
+        self.payout += other.payout;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkuPnlRow {
+    /// id номенклатуры (a004) либо `article:<артикул>` для несопоставленных строк.
+    pub sku_key: String,
+    pub nomenclature_ref: Option<String>,
+    pub article: String,
+    pub description: String,
+    pub current: SkuPnlMetrics,
+    pub previous: SkuPnlMetrics,
+    /// Выплата по отрезкам текущего периода, см. [`trend_bucket`].
+    pub trend: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkuPnlResponse {
+    pub filters: SkuPnlRequest,
+    /// Предыдущий период той же длины (`YYYY-MM-DD`).
+    pub previous_from: String,
+    pub previous_to: String,
+    pub totals: SkuPnlMetrics,
+    pub previous_totals: SkuPnlMetrics,
+    /// Сначала SKU с наибольшей выплатой за период.
+    pub rows: Vec<SkuPnlRow>,
+}
+
+/// Предыдущий период той же длины, вплотную до `date_from`.
+pub fn previous_period(date_from: NaiveDate, date_to: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let days = (date_to - date_from).num_days() + 1;
+    let previous_to = date_from - Duration::days(1);
+    (previous_to - Duration::days(days - 1), previous_to)
+}
+
+/// Изменение к предыдущему периоду, %. `None` — в предыдущем периоде был ноль.
+pub fn delta_percent(current: f64, previous: f64) -> Option<f64> {
+    if previous.abs() < 0.005 {
+        return None;
+    }
+    Some((current - previous) / previous.abs() * 100.0)
+}
+
+/// Число точек тренда для периода.
+pub fn trend_bucket_count(date_from: NaiveDate, date_to: NaiveDate) -> usize {
+    ((date_to - date_from).num_days() + 1).clamp(1, TREND_BUCKETS as i64) as usize
+}
+
+/// Отрезок тренда, в который попадает день; `None` — день вне периода.
+pub fn trend_bucket(day: NaiveDate, date_from: NaiveDate, date_to: NaiveDate) -> Option<usize> {
+    if day < date_from || day > date_to {
+        return None;
+    }
+    let days = (date_to - date_from).num_days() + 1;
+    let offset = (day - date_from).num_days();
+    let buckets = trend_bucket_count(date_from, date_to) as i64;
+    Some((offset * buckets / days) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn previous_period_has_same_length() {
+        assert_eq!(
+            previous_period(date("2026-03-01"), date("2026-03-31")),
+            (date("2026-01-29"), date("2026-02-28"))
+        );
+        assert_eq!(
+            previous_period(date("2026-03-10"), date("2026-03-10")),
+            (date("2026-03-09"), date("2026-03-09"))
+        );
+    }
+
+    #[test]
+    fn delta_needs_previous_value() {
+        assert_eq!(delta_percent(150.0, 100.0), Some(50.0));
+        assert_eq!(delta_percent(-50.0, -100.0), Some(50.0));
+        assert_eq!(delta_percent(100.0, 0.0), None);
+    }
+
+    #[test]
+    fn trend_buckets_cover_period() {
+        let (from, to) = (date("2026-03-01"), date("2026-03-31"));
+        assert_eq!(trend_bucket_count(from, to), TREND_BUCKETS);
+        assert_eq!(trend_bucket(from, from, to), Some(0));
+        assert_eq!(trend_bucket(to, from, to), Some(TREND_BUCKETS - 1));
+        assert_eq!(trend_bucket(date("2026-02-28"), from, to), None);
+
+        let week_end = date("2026-03-07");
+        assert_eq!(trend_bucket_count(from, week_end), 7);
+        assert_eq!(trend_bucket(date("2026-03-04"), from, week_end), Some(3));
+    }
+}
@@ -3,3 +3,10 @@ pub mod d402_wb_order_flow;
 pub mod d403_ym_order_flow;
 pub mod d404_wb_advert_report;
 pub mod d406_wb_sales_funnel;
+pub mod d407_wb_supply_acceptance;
+pub mod d408_pnl_statement;
+pub mod d409_margin_scenario;
+pub mod d410_sku_launch_cohorts;
+pub mod d412_demand_forecast;
+pub mod d414_import_coverage;
+pub mod d415_sku_pnl;
@@ -223,7 +223,7 @@ impl AggregateRoot for Organization {
 // ============================================================================
 
 /// DTO для создания/обновления организации
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct OrganizationDto {
     pub id: Option<String>,
     pub code: Option<String>,
@@ -190,7 +190,7 @@ impl AggregateRoot for Counterparty {
 // ============================================================================
 // DTO
 // ============================================================================
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct CounterpartyDto {
     pub id: Option<String>,
     pub code: Option<String>,
@@ -132,8 +132,10 @@ pub struct ConnectionMP {
     #[serde(rename = "API_Key_Статистика")]
     pub api_key_stats: Option<String>,
 
+    /// Песочница: запросы уходят на sandbox-эндпоинты маркетплейса (или mock-сервер),
+    /// импортированные документы считаются тестовыми и не проводятся в проекции.
     #[serde(rename = "ТестовыйРежим")]
-    pub test_mode: bool,
+    pub is_sandbox: bool,
 
     #[serde(rename = "ПлановыйПроцентКомиссии")]
     pub planned_commission_percent: Option<f64>,
@@ -168,7 +170,7 @@ impl ConnectionMP {
             is_used: false,
             business_account_id: None,
             api_key_stats: None,
-            test_mode: false,
+            is_sandbox: false,
             planned_commission_percent: None,
             planned_acquiring_percent: None,
             authorization_type: AuthorizationType::default(),
@@ -198,7 +200,7 @@ impl ConnectionMP {
         self.is_used = dto.is_used;
         self.business_account_id = dto.business_account_id.clone();
         self.api_key_stats = dto.api_key_stats.clone();
-        self.test_mode = dto.test_mode;
+        self.is_sandbox = dto.is_sandbox;
         self.planned_commission_percent = dto.planned_commission_percent;
         self.planned_acquiring_percent = dto.planned_acquiring_percent;
         self.authorization_type = dto.authorization_type.clone();
@@ -316,7 +318,7 @@ pub struct ConnectionMPDto {
     pub api_key_stats: Option<String>,
 
     #[serde(rename = "ТестовыйРежим")]
-    pub test_mode: bool,
+    pub is_sandbox: bool,
 
     #[serde(rename = "ПлановыйПроцентКомиссии")]
     pub planned_commission_percent: Option<f64>,
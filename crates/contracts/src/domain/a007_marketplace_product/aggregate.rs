@@ -312,4 +312,310 @@ pub struct MarketplaceProductListItemDto {
     pub nomenclature_ref: Option<String>,
     pub is_posted: bool,
     pub created_at: String,
+    /// Складской алерт последнего расчёта task026 (бейдж в списке).
+    #[serde(default)]
+    pub stock_alert: Option<StockAlertBadgeDto>,
+    /// ABC/XYZ-класс последнего рассчитанного квартала (task027).
+    #[serde(default)]
+    pub abc_xyz: Option<AbcXyzLabelDto>,
+}
+
+/// Вид складского алерта по товару.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StockAlertKind {
+    /// Запаса хватит меньше чем на заданное число дней при текущей скорости продаж.
+    LowCover,
+    /// Остаток есть, а продаж не было дольше заданного срока.
+    StaleStock,
+}
+
+impl StockAlertKind {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::LowCover => "low_cover",
+            Self::StaleStock => "stale_stock",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "low_cover" => Some(Self::LowCover),
+            "stale_stock" => Some(Self::StaleStock),
+            _ => None,
+        }
+    }
+}
+
+/// Складской алерт для бейджа в списке товаров.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockAlertBadgeDto {
+    pub kind: StockAlertKind,
+    /// Остаток (склады WB + продавца), шт.
+    pub stock: i64,
+    /// На сколько дней хватит остатка; None — продаж за окно не было.
+    pub days_of_cover: Option<f64>,
+    /// Дата последней продажи `YYYY-MM-DD`, если была.
+    pub last_sale_date: Option<String>,
+}
+
+/// ABC-класс товара по доле в выручке квартала.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AbcClass {
+    A,
+    B,
+    C,
+}
+
+impl AbcClass {
+    pub const ALL: [AbcClass; 3] = [AbcClass::A, AbcClass::B, AbcClass::C];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::A => "A",
+            Self::B => "B",
+            Self::C => "C",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim() {
+            "A" | "a" => Some(Self::A),
+            "B" | "b" => Some(Self::B),
+            "C" | "c" => Some(Self::C),
+            _ => None,
+        }
+    }
+}
+
+/// XYZ-класс товара по стабильности спроса (коэффициент вариации недельных продаж).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum XyzClass {
+    X,
+    Y,
+    Z,
+}
+
+impl XyzClass {
+    pub const ALL: [XyzClass; 3] = [XyzClass::X, XyzClass::Y, XyzClass::Z];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::X => "X",
+            Self::Y => "Y",
+            Self::Z => "Z",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim() {
+            "X" | "x" => Some(Self::X),
+            "Y" | "y" => Some(Self::Y),
+            "Z" | "z" => Some(Self::Z),
+            _ => None,
+        }
+    }
+}
+
+/// ABC/XYZ-метка товара за квартал (для бейджей и фильтров).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbcXyzLabelDto {
+    /// Квартал `YYYY-Qn`.
+    pub quarter: String,
+    pub abc: AbcClass,
+    pub xyz: XyzClass,
+    pub revenue: f64,
+    /// Доля в выручке квартала, %.
+    pub revenue_share: f64,
+    /// Коэффициент вариации недельных продаж, %; None — продаж в штуках не было.
+    pub demand_cv: Option<f64>,
+}
+
+impl AbcXyzLabelDto {
+    /// Составной код, напр. `"AX"`, `"CZ"`.
+    pub fn code(&self) -> String {
+        format!("{}{}", self.abc.code(), self.xyz.code())
+    }
+
+    /// Попадает ли метка под фильтр по классам (`None` — любой класс).
+    pub fn matches(&self, abc: Option<AbcClass>, xyz: Option<XyzClass>) -> bool {
+        abc.map_or(true, |abc| abc == self.abc) && xyz.map_or(true, |xyz| xyz == self.xyz)
+    }
+}
+
+/// Направление аномалии продаж относительно ожидания.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SalesAnomalyDirection {
+    /// Продаж заметно больше обычного для этого дня недели.
+    Spike,
+    /// Продаж заметно меньше обычного.
+    Drop,
+}
+
+impl SalesAnomalyDirection {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Spike => "spike",
+            Self::Drop => "drop",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "spike" => Some(Self::Spike),
+            "drop" => Some(Self::Drop),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Spike => "Всплеск",
+            Self::Drop => "Провал",
+        }
+    }
+}
+
+/// Аномалия продаж за день (task028) для списка на разбор.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SalesAnomalyDto {
+    pub id: String,
+    pub connection_mp_ref: String,
+    /// Товар a007; None — аномалия итога продаж кабинета за день.
+    pub marketplace_product_ref: Option<String>,
+    pub article: Option<String>,
+    pub product_name: Option<String>,
+    /// `YYYY-MM-DD`
+    pub sale_date: String,
+    pub direction: SalesAnomalyDirection,
+    pub actual_qty: f64,
+    /// Среднее за тот же день недели в предыдущие недели, шт.
+    pub expected_qty: f64,
+    pub std_dev: f64,
+    pub z_score: f64,
+    pub actual_revenue: f64,
+    pub expected_revenue: f64,
+    pub detected_at: String,
+    /// Пояснение пользователя; Some — аномалия разобрана.
+    pub explanation: Option<String>,
+    pub explained_by: Option<String>,
+    pub explained_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SalesAnomalyListResponse {
+    pub items: Vec<SalesAnomalyDto>,
+    /// Неразобранных аномалий всего (без учёта фильтров периода).
+    pub open_count: i64,
+}
+
+/// Пометить аномалию объяснённой.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplainSalesAnomalyRequest {
+    pub explanation: String,
+}
+
+/// Товар маркетплейса в сопоставлении между маркетплейсами.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossMappingProductDto {
+    pub id: String,
+    /// Код маркетплейса (`mp-wb`, `mp-ozon`, ...); пусто — тип маркетплейса не задан.
+    pub marketplace_code: String,
+    pub connection_mp_ref: String,
+    pub marketplace_sku: String,
+    pub article: String,
+    pub barcode: Option<String>,
+    pub description: String,
+    pub nomenclature_ref: Option<String>,
+}
+
+/// Товары разных маркетплейсов, ссылающиеся на одну номенклатуру.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossMappingGroupDto {
+    pub nomenclature_ref: String,
+    pub nomenclature_article: Option<String>,
+    pub nomenclature_name: Option<String>,
+    /// Коды маркетплейсов группы, по алфавиту.
+    pub marketplace_codes: Vec<String>,
+    pub products: Vec<CrossMappingProductDto>,
+}
+
+impl CrossMappingGroupDto {
+    /// Номенклатура продаётся больше чем на одном маркетплейсе.
+    pub fn is_cross_marketplace(&self) -> bool {
+        self.marketplace_codes.len() > 1
+    }
+}
+
+/// Номенклатура, на которую штрихкод указывает из каких-либо источников.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BarcodeClaimDto {
+    pub nomenclature_ref: String,
+    pub nomenclature_article: Option<String>,
+    pub nomenclature_name: Option<String>,
+    /// Источники: `1C`/`OZON`/`WB`/`YM` из p901 и коды маркетплейсов товаров a007.
+    pub sources: Vec<String>,
+}
+
+/// Конфликт: один штрихкод сопоставлен с несколькими номенклатурами.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BarcodeConflictDto {
+    pub barcode: String,
+    pub claims: Vec<BarcodeClaimDto>,
+    /// Товары маркетплейсов с этим штрихкодом.
+    pub products: Vec<CrossMappingProductDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossMappingResponse {
+    pub groups: Vec<CrossMappingGroupDto>,
+    pub conflicts: Vec<BarcodeConflictDto>,
+    /// Товаров маркетплейсов без номенклатуры.
+    pub unmapped_count: usize,
+}
+
+/// Выбрать номенклатуру для конфликтного штрихкода.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolveBarcodeConflictRequest {
+    pub barcode: String,
+    pub nomenclature_ref: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolveBarcodeConflictResponse {
+    /// Перепривязано товаров a007.
+    pub products_updated: u64,
+    /// Исправлено записей штрихкодов маркетплейсов в p901.
+    pub barcodes_updated: u64,
+}
+
+/// Цены товара WB за день: средние по заказам (a015) и продажам (a012).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceHistoryPointDto {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    /// Цена до скидок
+    pub total_price: Option<f64>,
+    /// Цена со скидкой продавца
+    pub price_with_disc: Option<f64>,
+    /// Скидка постоянного покупателя (СПП) WB, %
+    pub spp: Option<f64>,
+    /// Цена для покупателя после СПП
+    pub finished_price: Option<f64>,
+    /// Заказов и продаж за день
+    pub documents: i64,
+}
+
+impl PriceHistoryPointDto {
+    /// Софинансирование WB за единицу: разница между ценой продавца со скидкой
+    /// и ценой, которую заплатил покупатель.
+    pub fn wb_coinvestment(&self) -> Option<f64> {
+        Some(self.price_with_disc? - self.finished_price?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceHistoryResponse {
+    pub points: Vec<PriceHistoryPointDto>,
 }
@@ -1,6 +1,7 @@
 use crate::domain::common::{
     AggregateId, AggregateRoot, BaseAggregate, EntityMetadata, EventStore, Origin,
 };
+use crate::shared::access::FinanceFields;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -366,6 +367,14 @@ pub struct OzonReturnsListDto {
     pub is_posted: bool,
 }
 
+impl FinanceFields for OzonReturnsListDto {
+    const FINANCE_FIELDS: &'static [&'static str] = &["price"];
+
+    fn mask_finance(&mut self) {
+        self.price = 0.0;
+    }
+}
+
 // =============================================================================
 // Detail DTO for frontend
 // =============================================================================
@@ -408,6 +417,14 @@ pub struct OzonReturnsDetailDto {
     pub metadata: OzonReturnsMetadataDto,
 }
 
+impl FinanceFields for OzonReturnsDetailDto {
+    const FINANCE_FIELDS: &'static [&'static str] = &["price"];
+
+    fn mask_finance(&mut self) {
+        self.price = 0.0;
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OzonReturnsMetadataDto {
     #[serde(rename = "createdAt")]
@@ -1,6 +1,7 @@
 use crate::domain::common::{
     AggregateId, AggregateRoot, BaseAggregate, EntityMetadata, EventStore, Origin,
 };
+use crate::shared::access::FinanceFields;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -120,6 +121,28 @@ pub struct OzonFbsPosting {
 
     /// Флаг проведения документа (для формирования проекций)
     pub is_posted: bool,
+
+    /// Тестовые данные: загружен через подключение в режиме песочницы
+    #[serde(default)]
+    pub is_sandbox: bool,
+}
+
+impl FinanceFields for OzonFbsPosting {
+    const FINANCE_FIELDS: &'static [&'static str] = &[
+        "price_list",
+        "discount_total",
+        "price_effective",
+        "amount_line",
+    ];
+
+    fn mask_finance(&mut self) {
+        for line in &mut self.lines {
+            line.price_list = None;
+            line.discount_total = None;
+            line.price_effective = None;
+            line.amount_line = None;
+        }
+    }
 }
 
 impl OzonFbsPosting {
@@ -140,6 +163,7 @@ impl OzonFbsPosting {
             state,
             source_meta,
             is_posted,
+            is_sandbox: false,
         }
     }
 
@@ -162,6 +186,7 @@ impl OzonFbsPosting {
             state,
             source_meta,
             is_posted,
+            is_sandbox: false,
         }
     }
 
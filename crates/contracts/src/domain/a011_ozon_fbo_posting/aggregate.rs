@@ -1,6 +1,7 @@
 use crate::domain::common::{
     AggregateId, AggregateRoot, BaseAggregate, EntityMetadata, EventStore, Origin,
 };
+use crate::shared::access::FinanceFields;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -122,6 +123,28 @@ pub struct OzonFboPosting {
 
     /// Флаг проведения документа (для формирования проекций)
     pub is_posted: bool,
+
+    /// Тестовые данные: загружен через подключение в режиме песочницы
+    #[serde(default)]
+    pub is_sandbox: bool,
+}
+
+impl FinanceFields for OzonFboPosting {
+    const FINANCE_FIELDS: &'static [&'static str] = &[
+        "price_list",
+        "discount_total",
+        "price_effective",
+        "amount_line",
+    ];
+
+    fn mask_finance(&mut self) {
+        for line in &mut self.lines {
+            line.price_list = None;
+            line.discount_total = None;
+            line.price_effective = None;
+            line.amount_line = None;
+        }
+    }
 }
 
 impl OzonFboPosting {
@@ -142,6 +165,7 @@ impl OzonFboPosting {
             state,
             source_meta,
             is_posted,
+            is_sandbox: false,
         }
     }
 
@@ -164,6 +188,7 @@ impl OzonFboPosting {
             state,
             source_meta,
             is_posted,
+            is_sandbox: false,
         }
     }
 
@@ -1,6 +1,7 @@
 use crate::domain::common::{
     AggregateId, AggregateRoot, BaseAggregate, EntityMetadata, EventStore, Origin,
 };
+use crate::shared::access::FinanceFields;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -152,6 +153,9 @@ pub struct WbSalesSourceMeta {
     pub fetched_at: DateTime<Utc>,
     /// Версия документа (для отслеживания изменений)
     pub document_version: i32,
+    /// Сессия импорта, последней записавшая документ (см. `sys_import_batches`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub import_session_id: Option<String>,
 }
 
 /// Документ Wildberries Sales (агрегат)
@@ -178,6 +182,10 @@ pub struct WbSales {
     /// Флаг проведения документа (для формирования проекций)
     pub is_posted: bool,
 
+    /// Тестовые данные: загружен через подключение в режиме песочницы
+    #[serde(default)]
+    pub is_sandbox: bool,
+
     /// Признак возврата покупателя (устанавливается при проведении)
     #[serde(default)]
     pub is_customer_return: bool,
@@ -222,6 +230,7 @@ impl WbSales {
             warehouse,
             source_meta,
             is_posted,
+            is_sandbox: false,
             is_customer_return: false,
             marketplace_product_ref: None,
             nomenclature_ref: None,
@@ -253,6 +262,7 @@ impl WbSales {
             warehouse,
             source_meta,
             is_posted,
+            is_sandbox: false,
             is_customer_return: false,
             marketplace_product_ref: None,
             nomenclature_ref: None,
@@ -292,6 +302,59 @@ impl WbSales {
     }
 }
 
+impl FinanceFields for WbSales {
+    const FINANCE_FIELDS: &'static [&'static str] = &[
+        "price_list",
+        "discount_total",
+        "price_effective",
+        "amount_line",
+        "total_price",
+        "payment_sale_amount",
+        "finished_price",
+        "sell_out_plan",
+        "sell_out_fact",
+        "acquiring_fee_plan",
+        "acquiring_fee_fact",
+        "other_fee_plan",
+        "other_fee_fact",
+        "supplier_payout_plan",
+        "supplier_payout_fact",
+        "profit_plan",
+        "profit_fact",
+        "cost_of_production",
+        "commission_plan",
+        "commission_fact",
+        "dealer_price_ut",
+        "prod_cost_resolved_total",
+    ];
+
+    fn mask_finance(&mut self) {
+        let line = &mut self.line;
+        line.price_list = None;
+        line.discount_total = None;
+        line.price_effective = None;
+        line.amount_line = None;
+        line.total_price = None;
+        line.payment_sale_amount = None;
+        line.finished_price = None;
+        line.sell_out_plan = None;
+        line.sell_out_fact = None;
+        line.acquiring_fee_plan = None;
+        line.acquiring_fee_fact = None;
+        line.other_fee_plan = None;
+        line.other_fee_fact = None;
+        line.supplier_payout_plan = None;
+        line.supplier_payout_fact = None;
+        line.profit_plan = None;
+        line.profit_fact = None;
+        line.cost_of_production = None;
+        line.commission_plan = None;
+        line.commission_fact = None;
+        line.dealer_price_ut = None;
+        self.prod_cost_resolved_total = None;
+    }
+}
+
 impl AggregateRoot for WbSales {
     type Id = WbSalesId;
 
@@ -343,3 +406,21 @@ impl AggregateRoot for WbSales {
         Origin::Marketplace
     }
 }
+
+/// Пакетное проведение / отмена проведения продаж WB
+/// (`POST /api/a012/wb-sales/batch-post`, `/batch-unpost`): явный список `ids`
+/// или, если он пуст, все продажи с датой продажи в `date_from..=date_to`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WbSalesBatchRequest {
+    #[serde(default)]
+    pub ids: Vec<String>,
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+}
+
+/// Пакет поставлен в очередь: прогресс и итог — `GET /api/operations/{operation_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WbSalesBatchResponse {
+    pub operation_id: String,
+    pub document_count: usize,
+}
@@ -0,0 +1,77 @@
+use crate::shared::calc_column::CalcField;
+
+/// Числовые поля списка WB Sales для вычисляемых колонок (`?calc=`).
+pub const CALC_FIELDS: &[CalcField] = &[
+    CalcField {
+        key: "qty",
+        label: "Количество",
+    },
+    CalcField {
+        key: "amount_line",
+        label: "К выплате",
+    },
+    CalcField {
+        key: "total_price",
+        label: "Полная цена",
+    },
+    CalcField {
+        key: "finished_price",
+        label: "Итоговая цена",
+    },
+    CalcField {
+        key: "dealer_price_ut",
+        label: "Дилерская цена УТ",
+    },
+    CalcField {
+        key: "cost_of_production",
+        label: "Себестоимость",
+    },
+    CalcField {
+        key: "sell_out_plan",
+        label: "Реализация (план)",
+    },
+    CalcField {
+        key: "sell_out_fact",
+        label: "Реализация (факт)",
+    },
+    CalcField {
+        key: "commission_plan",
+        label: "Комиссия (план)",
+    },
+    CalcField {
+        key: "commission_fact",
+        label: "Комиссия (факт)",
+    },
+    CalcField {
+        key: "acquiring_fee_plan",
+        label: "Эквайринг (план)",
+    },
+    CalcField {
+        key: "acquiring_fee_fact",
+        label: "Эквайринг (факт)",
+    },
+    CalcField {
+        key: "other_fee_plan",
+        label: "Прочие удержания (план)",
+    },
+    CalcField {
+        key: "other_fee_fact",
+        label: "Прочие удержания (факт)",
+    },
+    CalcField {
+        key: "supplier_payout_plan",
+        label: "К перечислению (план)",
+    },
+    CalcField {
+        key: "supplier_payout_fact",
+        label: "К перечислению (факт)",
+    },
+    CalcField {
+        key: "profit_plan",
+        label: "Прибыль (план)",
+    },
+    CalcField {
+        key: "profit_fact",
+        label: "Прибыль (факт)",
+    },
+];
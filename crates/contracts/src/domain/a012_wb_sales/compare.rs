@@ -0,0 +1,128 @@
+//! Сравнение продаж WB «колонками»: ключевые поля нескольких документов рядом,
+//! чтобы увидеть, почему похожие продажи проведены с разной выплатой.
+
+use crate::shared::access::FinanceFields;
+use crate::shared::marketplace_links::MarketplaceLinkDto;
+use serde::{Deserialize, Serialize};
+
+/// Максимум документов на экране сравнения.
+pub const MAX_COMPARE_DOCUMENTS: usize = 5;
+
+/// Как фронтенд форматирует значения строки.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompareValueKind {
+    Text,
+    /// Сумма, значение — число в виде строки (`"1234.5"`).
+    Money,
+    /// RFC3339 UTC.
+    DateTime,
+}
+
+/// Документ-колонка.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WbSalesCompareColumn {
+    pub id: String,
+    pub document_no: String,
+    pub is_posted: bool,
+    #[serde(default)]
+    pub marketplace_links: Vec<MarketplaceLinkDto>,
+}
+
+/// Одно поле по всем документам; `values` в порядке колонок.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WbSalesCompareRow {
+    pub group: String,
+    pub label: String,
+    pub kind: CompareValueKind,
+    pub values: Vec<Option<String>>,
+    /// Значения различаются хотя бы у двух документов.
+    pub differs: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WbSalesCompareResponse {
+    pub columns: Vec<WbSalesCompareColumn>,
+    pub rows: Vec<WbSalesCompareRow>,
+}
+
+impl FinanceFields for WbSalesCompareResponse {
+    /// Суммы — строки вида [`CompareValueKind::Money`], а не отдельные поля.
+    const FINANCE_FIELDS: &'static [&'static str] = &[];
+
+    /// Без доступа к финансам строки сумм не отдаются вовсе: пустая строка
+    /// выглядела бы как отсутствующее значение у всех документов.
+    fn mask_finance(&mut self) {
+        self.rows.retain(|row| row.kind != CompareValueKind::Money);
+    }
+}
+
+/// Разбирает `ids=a,b,c`: пробелы и повторы отбрасываются, документов от 2 до
+/// [`MAX_COMPARE_DOCUMENTS`].
+pub fn parse_compare_ids(raw: &str) -> Result<Vec<String>, String> {
+    let mut ids: Vec<String> = Vec::new();
+    for id in raw.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+        if !ids.iter().any(|existing| existing == id) {
+            ids.push(id.to_string());
+        }
+    }
+    if ids.len() < 2 {
+        return Err("Для сравнения выберите минимум 2 документа".to_string());
+    }
+    if ids.len() > MAX_COMPARE_DOCUMENTS {
+        return Err(format!(
+            "Сравнить можно не более {} документов",
+            MAX_COMPARE_DOCUMENTS
+        ));
+    }
+    Ok(ids)
+}
+
+/// Есть ли расхождение между документами. Отсутствующее значение отличается от заданного.
+pub fn values_differ(values: &[Option<String>]) -> bool {
+    values.windows(2).any(|pair| pair[0] != pair[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_trimmed_deduplicated_and_bounded() {
+        assert_eq!(
+            parse_compare_ids(" a, b ,a,,c").unwrap(),
+            vec!["a".to_string(), "b".to_string(), "c".to_string()]
+        );
+        assert!(parse_compare_ids("a,a").is_err());
+        assert!(parse_compare_ids("a,b,c,d,e,f").is_err());
+    }
+
+    #[test]
+    fn missing_value_counts_as_difference() {
+        let same = vec![Some("1".to_string()), Some("1".to_string())];
+        assert!(!values_differ(&same));
+        assert!(values_differ(&[Some("1".to_string()), None]));
+    }
+
+    #[test]
+    fn masking_drops_money_rows() {
+        let row = |label: &str, kind: CompareValueKind| WbSalesCompareRow {
+            group: "Суммы".to_string(),
+            label: label.to_string(),
+            kind,
+            values: vec![Some("100.00".to_string()), Some("90.00".to_string())],
+            differs: true,
+        };
+        let mut response = WbSalesCompareResponse {
+            columns: Vec::new(),
+            rows: vec![
+                row("Цена для покупателя", CompareValueKind::Money),
+                row("СПП, %", CompareValueKind::Text),
+                row("К перечислению", CompareValueKind::Money),
+            ],
+        };
+        response.mask_finance();
+        let labels: Vec<&str> = response.rows.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["СПП, %"]);
+    }
+}
@@ -0,0 +1,29 @@
+//! Ручная привязка строк финансового отчёта WB (p903) к продаже.
+//!
+//! Автоматически вкладка «Связи» находит строки p903 по SRID. Если SRID в отчёте
+//! не совпадает с документом (пустой, другой формат), строку можно привязать
+//! вручную — связь хранится по `rrd_id` и переживает перезагрузку отчёта.
+
+use crate::projections::p903_wb_finance_report::dto::WbFinanceReportDto;
+use serde::{Deserialize, Serialize};
+
+/// Максимум строк в одном запросе привязки/отвязки.
+pub const MAX_FINANCE_LINKS_PER_REQUEST: usize = 500;
+
+/// Окно поиска кандидатов вокруг даты продажи, дней в каждую сторону.
+pub const FINANCE_LINK_CANDIDATE_DAYS: i64 = 45;
+
+/// Тело `POST/DELETE /api/a012/wb-sales/{id}/finance-links`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WbSalesFinanceLinksRequest {
+    pub rrd_ids: Vec<i64>,
+}
+
+/// Строки p903, привязанные к продаже вручную.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WbSalesFinanceLinksResponse {
+    pub rows: Vec<WbFinanceReportDto>,
+    /// Привязанные `rrd_id`, строк которых сейчас нет в p903 (отчёт ещё не перезагружен)
+    #[serde(default)]
+    pub missing_rrd_ids: Vec<i64>,
+}
@@ -1,4 +1,8 @@
 pub mod aggregate;
+pub mod calc_fields;
+pub mod compare;
+pub mod finance_links;
+pub mod quick_filter;
 
 // Generated by build.rs from metadata.json
 #[allow(dead_code)]
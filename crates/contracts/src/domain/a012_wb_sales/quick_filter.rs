@@ -0,0 +1,59 @@
+use crate::shared::quick_filter::{QuickFilterField, QuickFilterKind};
+
+/// Поля быстрого фильтра списка WB Sales (`?q=`).
+pub const QUICK_FILTER_FIELDS: &[QuickFilterField] = &[
+    QuickFilterField {
+        key: "status",
+        label: "Проведение",
+        kind: QuickFilterKind::Enum(&["posted", "unposted"]),
+        finance: false,
+    },
+    QuickFilterField {
+        key: "type",
+        label: "Тип операции",
+        kind: QuickFilterKind::Enum(&["sale", "return"]),
+        finance: false,
+    },
+    QuickFilterField {
+        key: "warehouse",
+        label: "Склад",
+        kind: QuickFilterKind::Text,
+        finance: false,
+    },
+    QuickFilterField {
+        key: "article",
+        label: "Артикул продавца",
+        kind: QuickFilterKind::Text,
+        finance: false,
+    },
+    QuickFilterField {
+        key: "srid",
+        label: "SRID",
+        kind: QuickFilterKind::Text,
+        finance: false,
+    },
+    QuickFilterField {
+        key: "nm",
+        label: "Артикул WB (nmID)",
+        kind: QuickFilterKind::Number,
+        finance: false,
+    },
+    QuickFilterField {
+        key: "amount",
+        label: "Сумма строки",
+        kind: QuickFilterKind::Number,
+        finance: true,
+    },
+    QuickFilterField {
+        key: "qty",
+        label: "Количество",
+        kind: QuickFilterKind::Number,
+        finance: false,
+    },
+    QuickFilterField {
+        key: "date",
+        label: "Дата продажи",
+        kind: QuickFilterKind::Date,
+        finance: false,
+    },
+];
@@ -1,6 +1,7 @@
 use crate::domain::common::{
     AggregateId, AggregateRoot, BaseAggregate, EntityMetadata, EventStore, Origin,
 };
+use crate::shared::access::FinanceFields;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -188,6 +189,10 @@ pub struct YmOrder {
     /// Флаг проведения документа (для формирования проекций)
     pub is_posted: bool,
 
+    /// Тестовые данные: загружен через подключение в режиме песочницы
+    #[serde(default)]
+    pub is_sandbox: bool,
+
     /// Флаг ошибки (ненулевой при отсутствии сопоставления номенклатуры в строках)
     #[serde(default)]
     pub is_error: bool,
@@ -211,6 +216,7 @@ impl YmOrder {
             state,
             source_meta,
             is_posted,
+            is_sandbox: false,
             is_error: false,
         }
     }
@@ -234,6 +240,7 @@ impl YmOrder {
             state,
             source_meta,
             is_posted,
+            is_sandbox: false,
             is_error: false,
         }
     }
@@ -394,3 +401,61 @@ pub struct YmOrderListDto {
     #[serde(default)]
     pub payment_date: String,
 }
+
+impl FinanceFields for YmOrderListDto {
+    const FINANCE_FIELDS: &'static [&'static str] = &[
+        "total_amount",
+        "total_amount_api",
+        "delivery_total",
+        "subsidies_total",
+        "total_dealer_amount",
+        "margin_pro",
+    ];
+
+    fn mask_finance(&mut self) {
+        self.total_amount = 0.0;
+        self.total_amount_api = None;
+        self.delivery_total = None;
+        self.subsidies_total = 0.0;
+        self.total_dealer_amount = None;
+        self.margin_pro = None;
+    }
+}
+
+impl FinanceFields for YmOrder {
+    const FINANCE_FIELDS: &'static [&'static str] = &[
+        "total_amount",
+        "items_total",
+        "delivery_total",
+        "subsidies_json",
+        "total_dealer_amount",
+        "margin_pro",
+        "price_list",
+        "discount_total",
+        "price_effective",
+        "amount_line",
+        "buyer_price",
+        "price_plan",
+        "dealer_price_ut",
+    ];
+
+    fn mask_finance(&mut self) {
+        let header = &mut self.header;
+        header.total_amount = None;
+        header.items_total = None;
+        header.delivery_total = None;
+        header.subsidies_json = None;
+        header.total_dealer_amount = None;
+        header.margin_pro = None;
+        for line in &mut self.lines {
+            line.price_list = None;
+            line.discount_total = None;
+            line.price_effective = None;
+            line.amount_line = None;
+            line.buyer_price = None;
+            line.subsidies_json = None;
+            line.price_plan = None;
+            line.dealer_price_ut = None;
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::domain::common::{
     AggregateId, AggregateRoot, BaseAggregate, EntityMetadata, EventStore, Origin,
 };
+use crate::shared::access::FinanceFields;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -171,6 +172,9 @@ pub struct WbOrdersSourceMeta {
     pub fetched_at: DateTime<Utc>,
     /// Версия документа (для отслеживания изменений)
     pub document_version: i32,
+    /// Сессия импорта, последней записавшая документ (см. `sys_import_batches`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub import_session_id: Option<String>,
 }
 
 /// Документ Wildberries Orders (агрегат)
@@ -200,6 +204,10 @@ pub struct WbOrders {
     /// Флаг проведения документа (для формирования проекций)
     pub is_posted: bool,
 
+    /// Тестовые данные: загружен через подключение в режиме песочницы
+    #[serde(default)]
+    pub is_sandbox: bool,
+
     /// Ссылка на товар маркетплейса (a007_marketplace_product)
     pub marketplace_product_ref: Option<String>,
 
@@ -237,6 +245,7 @@ impl WbOrders {
             geography,
             source_meta,
             is_posted,
+            is_sandbox: false,
             marketplace_product_ref: None,
             nomenclature_ref: None,
             base_nomenclature_ref: None,
@@ -268,6 +277,7 @@ impl WbOrders {
             geography,
             source_meta,
             is_posted,
+            is_sandbox: false,
             marketplace_product_ref: None,
             nomenclature_ref: None,
             base_nomenclature_ref: None,
@@ -304,6 +314,29 @@ impl WbOrders {
     }
 }
 
+impl FinanceFields for WbOrders {
+    const FINANCE_FIELDS: &'static [&'static str] = &[
+        "total_price",
+        "finished_price",
+        "price_with_disc",
+        "price",
+        "sale_price",
+        "dealer_price_ut",
+        "margin_pro",
+    ];
+
+    fn mask_finance(&mut self) {
+        let line = &mut self.line;
+        line.total_price = None;
+        line.finished_price = None;
+        line.price_with_disc = None;
+        line.price = None;
+        line.sale_price = None;
+        line.dealer_price_ut = None;
+        line.margin_pro = None;
+    }
+}
+
 impl AggregateRoot for WbOrders {
     type Id = WbOrdersId;
 
@@ -1,4 +1,5 @@
 pub mod aggregate;
+pub mod quick_filter;
 
 // Generated by build.rs from metadata.json
 #[allow(dead_code)]
@@ -0,0 +1,53 @@
+use crate::shared::quick_filter::{QuickFilterField, QuickFilterKind};
+
+/// Поля быстрого фильтра списка WB Orders (строка поиска `search_query`).
+pub const QUICK_FILTER_FIELDS: &[QuickFilterField] = &[
+    QuickFilterField {
+        key: "status",
+        label: "Проведение",
+        kind: QuickFilterKind::Enum(&["posted", "unposted"]),
+        finance: false,
+    },
+    QuickFilterField {
+        key: "cancel",
+        label: "Отменён",
+        kind: QuickFilterKind::Enum(&["yes", "no"]),
+        finance: false,
+    },
+    QuickFilterField {
+        key: "warehouse",
+        label: "Склад",
+        kind: QuickFilterKind::Text,
+        finance: false,
+    },
+    QuickFilterField {
+        key: "article",
+        label: "Артикул продавца",
+        kind: QuickFilterKind::Text,
+        finance: false,
+    },
+    QuickFilterField {
+        key: "brand",
+        label: "Бренд",
+        kind: QuickFilterKind::Text,
+        finance: false,
+    },
+    QuickFilterField {
+        key: "amount",
+        label: "Цена с учётом скидок",
+        kind: QuickFilterKind::Number,
+        finance: true,
+    },
+    QuickFilterField {
+        key: "qty",
+        label: "Количество",
+        kind: QuickFilterKind::Number,
+        finance: false,
+    },
+    QuickFilterField {
+        key: "date",
+        label: "Дата заказа",
+        kind: QuickFilterKind::Date,
+        finance: false,
+    },
+];
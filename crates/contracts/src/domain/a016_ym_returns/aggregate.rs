@@ -1,6 +1,7 @@
 use crate::domain::common::{
     AggregateId, AggregateRoot, BaseAggregate, EntityMetadata, EventStore, Origin,
 };
+use crate::shared::access::FinanceFields;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -150,6 +151,10 @@ pub struct YmReturn {
 
     /// Флаг проведения документа (для формирования проекций)
     pub is_posted: bool,
+
+    /// Тестовые данные: загружен через подключение в режиме песочницы
+    #[serde(default)]
+    pub is_sandbox: bool,
 }
 
 impl YmReturn {
@@ -170,6 +175,7 @@ impl YmReturn {
             state,
             source_meta,
             is_posted,
+            is_sandbox: false,
         }
     }
 
@@ -192,6 +198,7 @@ impl YmReturn {
             state,
             source_meta,
             is_posted,
+            is_sandbox: false,
         }
     }
 
@@ -323,3 +330,27 @@ pub struct YmReturnListItemDto {
     pub fetched_at: String,
     pub is_posted: bool,
 }
+
+impl FinanceFields for YmReturnListItemDto {
+    const FINANCE_FIELDS: &'static [&'static str] = &["total_amount"];
+
+    fn mask_finance(&mut self) {
+        self.total_amount = 0.0;
+    }
+}
+
+impl FinanceFields for YmReturn {
+    const FINANCE_FIELDS: &'static [&'static str] =
+        &["amount", "price", "partner_compensation_amount"];
+
+    fn mask_finance(&mut self) {
+        self.header.amount = None;
+        for line in &mut self.lines {
+            line.price = None;
+            for decision in &mut line.decisions {
+                decision.amount = None;
+                decision.partner_compensation_amount = None;
+            }
+        }
+    }
+}
@@ -166,6 +166,9 @@ pub struct YmRealization {
     pub return_lines: Vec<YmRealizationLine>,
     pub source_meta: YmRealizationSourceMeta,
     pub is_posted: bool,
+    /// Тестовые данные: загружен через подключение в режиме песочницы
+    #[serde(default)]
+    pub is_sandbox: bool,
 }
 
 impl YmRealization {
@@ -194,6 +197,7 @@ impl YmRealization {
             return_lines,
             source_meta,
             is_posted: false,
+            is_sandbox: false,
         }
     }
 
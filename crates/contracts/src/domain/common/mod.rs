@@ -6,6 +6,7 @@ pub mod base_aggregate;
 pub mod entity_metadata;
 pub mod event_store;
 pub mod origin;
+pub mod version_conflict;
 
 // Re-exports
 pub use aggregate_id::AggregateId;
@@ -14,3 +15,4 @@ pub use base_aggregate::BaseAggregate;
 pub use entity_metadata::EntityMetadata;
 pub use event_store::EventStore;
 pub use origin::Origin;
+pub use version_conflict::VersionConflict;
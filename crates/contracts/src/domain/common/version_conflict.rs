@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Тело ответа 409: документ изменён после того, как клиент его загрузил
+/// (optimistic locking по `EntityMetadata::version`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionConflict {
+    /// Версия, с которой работал клиент
+    pub expected_version: i32,
+    /// Текущая версия в БД
+    pub current_version: i32,
+}
+
+impl VersionConflict {
+    /// Сверить версию клиента с текущей. `None` — клиент версию не передал
+    /// (пакетные операции, старые клиенты), проверка не выполняется.
+    pub fn check(expected: Option<i32>, current: i32) -> Result<(), VersionConflict> {
+        match expected {
+            Some(expected_version) if expected_version != current => Err(VersionConflict {
+                expected_version,
+                current_version: current,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching_or_missing_version_passes() {
+        assert_eq!(VersionConflict::check(Some(3), 3), Ok(()));
+        assert_eq!(VersionConflict::check(None, 7), Ok(()));
+    }
+
+    #[test]
+    fn stale_version_is_rejected_with_current() {
+        assert_eq!(
+            VersionConflict::check(Some(2), 4),
+            Err(VersionConflict {
+                expected_version: 2,
+                current_version: 4,
+            })
+        );
+    }
+}
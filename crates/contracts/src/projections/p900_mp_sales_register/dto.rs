@@ -20,6 +20,9 @@ pub struct SalesRegisterDto {
     pub marketplace_product_ref: Option<String>,
     pub nomenclature_ref: Option<String>,
     pub registrator_ref: String,
+    /// Код агрегата документа-источника (`a012_wb_sales` …); `None` — тип не сопоставлен.
+    #[serde(default)]
+    pub source_type: Option<String>,
 
     // Timestamps and status
     pub event_time_source: String,
@@ -52,8 +55,34 @@ pub struct SalesRegisterDto {
     pub loaded_at_utc: String,
     pub payload_version: i32,
     pub extra: Option<String>,
+    /// Склад продажи; есть только у WB
+    #[serde(default)]
+    pub warehouse_name: Option<String>,
+}
+
+/// Фильтр по привязке строки к номенклатуре 1С
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NomenclatureLinkStatus {
+    /// `nomenclature_ref` заполнен
+    Linked,
+    /// Номенклатура не сопоставлена
+    Unlinked,
 }
 
+/// Колонки, по которым сервер сортирует список; остальные значения `sort_by` — по дате.
+pub const SALES_REGISTER_SORT_FIELDS: &[&str] = &[
+    "sale_date",
+    "marketplace",
+    "document_no",
+    "seller_sku",
+    "title",
+    "warehouse_name",
+    "status_norm",
+    "qty",
+    "amount_line",
+];
+
 /// Request для получения списка продаж с фильтрами
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SalesRegisterListRequest {
@@ -69,6 +98,17 @@ pub struct SalesRegisterListRequest {
     pub status_norm: Option<String>,
     #[serde(default)]
     pub seller_sku: Option<String>,
+    #[serde(default)]
+    pub link_status: Option<NomenclatureLinkStatus>,
+    /// Склад: поиск по вхождению
+    #[serde(default)]
+    pub warehouse: Option<String>,
+    /// См. [`SALES_REGISTER_SORT_FIELDS`]; по умолчанию `sale_date`
+    #[serde(default)]
+    pub sort_by: Option<String>,
+    /// По умолчанию — по убыванию
+    #[serde(default)]
+    pub sort_desc: Option<bool>,
     #[serde(default = "default_limit")]
     pub limit: i32,
     #[serde(default)]
@@ -111,6 +151,36 @@ pub struct SalesRegisterStatsByDateResponse {
     pub data: Vec<DailyStat>,
 }
 
+/// Query для sparkline продаж товара МП (a007)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SkuSparklineRequest {
+    /// Последний день окна, `YYYY-MM-DD`; по умолчанию — сегодня (UTC)
+    #[serde(default)]
+    pub date_to: Option<String>,
+    /// Длина окна в днях; по умолчанию 30
+    #[serde(default)]
+    pub days: Option<u32>,
+}
+
+/// Продажи товара за один день (дни без продаж — с нулями)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkuSparklinePoint {
+    pub date: String,
+    pub qty: f64,
+    pub revenue: f64,
+}
+
+/// Sparkline продаж товара МП за окно дней
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkuSparklineResponse {
+    pub marketplace_product_ref: String,
+    pub date_from: String,
+    pub date_to: String,
+    pub points: Vec<SkuSparklinePoint>,
+    pub total_qty: f64,
+    pub avg_daily_qty: f64,
+}
+
 /// Статистика по маркетплейсам
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketplaceStat {
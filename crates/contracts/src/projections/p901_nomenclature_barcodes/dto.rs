@@ -45,6 +45,8 @@ pub struct BarcodesByNomenclatureResponse {
 /// Запрос на получение списка штрихкодов с фильтрами
 #[derive(Debug, Deserialize)]
 pub struct BarcodeListRequest {
+    /// Точное совпадение штрихкода (поиск сканером), по всем источникам
+    pub barcode: Option<String>,
     pub nomenclature_ref: Option<String>,
     pub article: Option<String>,
     pub source: Option<String>,
@@ -103,6 +103,17 @@ pub struct WbFinanceReportListResponse {
     pub has_more: bool,
 }
 
+/// Итоги строк финансового отчета по одному SRID (вкладка связей a012/a015)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WbFinanceReportSridTotals {
+    pub count: i64,
+    pub ppvz_vw: f64,
+    pub ppvz_vw_nds: f64,
+    pub retail_amount: f64,
+    pub ppvz_for_pay: f64,
+    pub acquiring_fee: f64,
+}
+
 /// Ответ с деталями по одной записи
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WbFinanceReportDetailResponse {
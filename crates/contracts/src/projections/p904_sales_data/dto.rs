@@ -1,13 +1,16 @@
 use serde::{Deserialize, Serialize};
 
 /// DTO для записи Sales Data (P904)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SalesDataDto {
     pub id: String,
 
     // Technical fields
     pub registrator_ref: String,
     pub registrator_type: String,
+    /// Код агрегата документа-источника (`a012_wb_sales` …); `None` — тип не сопоставлен.
+    #[serde(default)]
+    pub source_type: Option<String>,
 
     // Dimensions
     pub date: String,
@@ -64,4 +67,87 @@ pub struct SalesDataListResponse {
     pub items: Vec<SalesDataDto>,
     pub total_count: i32,
     pub has_more: bool,
+    /// Итоги по `items`, посчитанные на сервере
+    #[serde(default)]
+    pub totals: SalesDataTotalsDto,
+}
+
+/// Итоги списка Sales Data по суммовым полям
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SalesDataTotalsDto {
+    pub count: i64,
+    pub customer_in: f64,
+    pub customer_out: f64,
+    pub coinvest_in: f64,
+    pub commission_out: f64,
+    pub acquiring_out: f64,
+    pub penalty_out: f64,
+    pub logistics_out: f64,
+    pub seller_out: f64,
+    pub price_full: f64,
+    pub price_list: f64,
+    pub price_return: f64,
+    pub commission_percent: f64,
+    pub coinvest_persent: f64,
+    pub total: f64,
+}
+
+/// Режим взаимозачёта возвратов WB с исходной продажей в P904
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnNettingMode {
+    /// Возврат проецируется по собственным полям документа (как раньше)
+    #[default]
+    Off,
+    /// Сторно строк продажи датой возврата
+    ReturnDate,
+    /// Сторно строк продажи датой исходной продажи: день продажи показывает чистый итог
+    SaleDate,
+}
+
+impl ReturnNettingMode {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ReturnNettingMode::Off => "off",
+            ReturnNettingMode::ReturnDate => "return_date",
+            ReturnNettingMode::SaleDate => "sale_date",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "off" => Some(ReturnNettingMode::Off),
+            "return_date" => Some(ReturnNettingMode::ReturnDate),
+            "sale_date" => Some(ReturnNettingMode::SaleDate),
+            _ => None,
+        }
+    }
+}
+
+/// Настройки взаимозачёта возвратов
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReturnNettingSettings {
+    pub mode: ReturnNettingMode,
+}
+
+/// Возврат WB, для которого не нашлась проведённая продажа
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmatchedReturnDto {
+    /// ID документа a012
+    pub id: String,
+    /// SRID
+    pub document_no: String,
+    pub sale_date: String,
+    pub supplier_article: String,
+    pub connection_mp_ref: String,
+    pub finished_price: Option<f64>,
+    /// `sale_not_found` | `sale_not_posted`
+    pub reason: String,
+}
+
+/// Отчёт по несопоставленным возвратам за период
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmatchedReturnsResponse {
+    pub items: Vec<UnmatchedReturnDto>,
+    pub total_count: i32,
 }
@@ -0,0 +1,27 @@
+//! DTO проекции `p917_return_stock_ledger`.
+
+use serde::{Deserialize, Serialize};
+
+/// Движение возвращённого товара по утверждённому решению (плоское зеркало строки БД).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReturnStockLedgerEntryDto {
+    pub id: String,
+    /// Дата движения (дата утверждения решения), YYYY-MM-DD
+    pub entry_date: String,
+    /// Код решения (`ReturnDecision::code`)
+    pub decision: String,
+    /// Агрегат документа возврата и его id
+    pub entity_type: String,
+    pub document_id: String,
+    pub line_no: i32,
+    pub organization_id: String,
+    /// Артикул маркетплейса: SKU Ozon, shopSku ЯМ, nmId WB
+    pub article: String,
+    pub product_name: String,
+    pub quantity: i32,
+    /// Изменение остатка склада: `quantity` при возврате на склад, иначе 0
+    pub stock_delta: i32,
+    /// Регистратор — решение `sys_return_dispositions.id`
+    pub registrator_ref: String,
+    pub created_at: String,
+}
@@ -0,0 +1,239 @@
+//! DTO проекции `p918_storage_cost_allocation` — распределение расходов на
+//! хранение и приёмку WB по SKU.
+//!
+//! WB начисляет хранение и платную приёмку строкой финансового отчёта без
+//! товара (уровень склада или поставки), и в p910 такой расход висит на
+//! кабинете. Распределение делит его между SKU по драйверу из правил, чтобы
+//! маржа товара учитывала свою долю хранения. Каждая строка p918 хранит
+//! драйвер, базу и долю — это и есть след распределения по строке расхода.
+
+use serde::{Deserialize, Serialize};
+
+/// Обороты p910, которые распределяются по SKU.
+pub const ALLOCATED_TURNOVER_CODES: &[&str] = &["mp_storage", "acceptance"];
+
+/// Драйвер строки, когда в отчёте WB товар уже указан: доля 100 %.
+pub const DRIVER_DIRECT: &str = "direct";
+
+/// Период выручки для драйвера «доля выручки»: N дней, заканчивая датой расхода.
+pub const REVENUE_WINDOW_DAYS: i64 = 30;
+
+/// Драйвер распределения.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AllocationDriver {
+    /// Остаток на складах WB (шт.) × объём единицы (л) из карточки товара
+    #[default]
+    VolumeShare,
+    /// Остаток на складах WB, шт.
+    UnitsStored,
+    /// Выручка продаж из финансового отчёта за `REVENUE_WINDOW_DAYS`
+    RevenueShare,
+}
+
+impl AllocationDriver {
+    pub const ALL: [AllocationDriver; 3] = [
+        AllocationDriver::VolumeShare,
+        AllocationDriver::UnitsStored,
+        AllocationDriver::RevenueShare,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AllocationDriver::VolumeShare => "volume_share",
+            AllocationDriver::UnitsStored => "units_stored",
+            AllocationDriver::RevenueShare => "revenue_share",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.code() == code)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AllocationDriver::VolumeShare => "Доля объёма",
+            AllocationDriver::UnitsStored => "Штук на хранении",
+            AllocationDriver::RevenueShare => "Доля выручки",
+        }
+    }
+}
+
+/// Драйвер по умолчанию: хранение WB тарифицируется по литрам, приёмка — по штукам.
+pub fn default_driver(turnover_code: &str) -> AllocationDriver {
+    match turnover_code {
+        "acceptance" => AllocationDriver::UnitsStored,
+        _ => AllocationDriver::VolumeShare,
+    }
+}
+
+/// Правило распределения оборота; без кабинета — для всех кабинетов.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StorageAllocationRule {
+    pub turnover_code: String,
+    #[serde(default)]
+    pub connection_mp_ref: Option<String>,
+    pub driver: AllocationDriver,
+}
+
+/// Правила распределения (хранятся в `sys_settings`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StorageAllocationSettings {
+    #[serde(default)]
+    pub rules: Vec<StorageAllocationRule>,
+}
+
+impl StorageAllocationSettings {
+    /// Драйвер для оборота кабинета: правило кабинета, затем общее, затем по умолчанию.
+    pub fn driver_for(&self, connection_mp_ref: &str, turnover_code: &str) -> AllocationDriver {
+        let for_code = || {
+            self.rules
+                .iter()
+                .filter(|r| r.turnover_code == turnover_code)
+        };
+        for_code()
+            .find(|r| r.connection_mp_ref.as_deref() == Some(connection_mp_ref))
+            .or_else(|| for_code().find(|r| r.connection_mp_ref.is_none()))
+            .map(|r| r.driver)
+            .unwrap_or_else(|| default_driver(turnover_code))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (index, rule) in self.rules.iter().enumerate() {
+            if !ALLOCATED_TURNOVER_CODES.contains(&rule.turnover_code.as_str()) {
+                return Err(format!(
+                    "Оборот {} не распределяется по SKU",
+                    rule.turnover_code
+                ));
+            }
+            let duplicate = self.rules[..index].iter().any(|other| {
+                other.turnover_code == rule.turnover_code
+                    && other.connection_mp_ref == rule.connection_mp_ref
+            });
+            if duplicate {
+                return Err(format!(
+                    "Повторяется правило для оборота {}",
+                    rule.turnover_code
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Доля расхода, отнесённая на SKU (плоское зеркало строки БД).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StorageCostAllocationDto {
+    pub id: String,
+    /// Строка расхода `p910_mp_unlinked_turnovers.id`
+    pub source_ref: String,
+    /// Строка финансового отчёта `p903_wb_finance_report.id`
+    pub registrator_ref: String,
+    pub connection_mp_ref: String,
+    pub entry_date: String,
+    pub turnover_code: String,
+    /// Сумма исходной строки расхода
+    pub source_amount: f64,
+    /// Код `AllocationDriver` или `DRIVER_DIRECT`
+    pub driver: String,
+    /// Дата снимка остатков a037 или период выручки, по которым посчитан драйвер
+    pub basis: String,
+    /// `None` — нераспределённый остаток (нет данных драйвера)
+    pub nm_id: Option<i64>,
+    pub article: String,
+    pub nomenclature_ref: Option<String>,
+    pub driver_value: f64,
+    pub driver_total: f64,
+    pub share: f64,
+    pub amount: f64,
+    pub comment: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageCostAllocationListResponse {
+    pub items: Vec<StorageCostAllocationDto>,
+    pub total_count: u64,
+}
+
+/// Перераспределение расходов за период по текущим правилам.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageAllocationRebuildRequest {
+    pub date_from: String,
+    pub date_to: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageAllocationRebuildResponse {
+    /// Обработано строк расхода p910
+    pub source_rows: usize,
+    /// Из них остались нераспределёнными (нет данных драйвера)
+    pub unallocated_rows: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(
+        code: &str,
+        connection: Option<&str>,
+        driver: AllocationDriver,
+    ) -> StorageAllocationRule {
+        StorageAllocationRule {
+            turnover_code: code.to_string(),
+            connection_mp_ref: connection.map(str::to_string),
+            driver,
+        }
+    }
+
+    #[test]
+    fn driver_for_prefers_cabinet_rule_then_general_then_default() {
+        let settings = StorageAllocationSettings {
+            rules: vec![
+                rule("mp_storage", None, AllocationDriver::RevenueShare),
+                rule("mp_storage", Some("cab-1"), AllocationDriver::UnitsStored),
+            ],
+        };
+        assert_eq!(
+            settings.driver_for("cab-1", "mp_storage"),
+            AllocationDriver::UnitsStored
+        );
+        assert_eq!(
+            settings.driver_for("cab-2", "mp_storage"),
+            AllocationDriver::RevenueShare
+        );
+        assert_eq!(
+            settings.driver_for("cab-1", "acceptance"),
+            AllocationDriver::UnitsStored
+        );
+        assert_eq!(
+            StorageAllocationSettings::default().driver_for("cab-1", "mp_storage"),
+            AllocationDriver::VolumeShare
+        );
+    }
+
+    #[test]
+    fn validate_rejects_unknown_codes_and_duplicates() {
+        let ok = StorageAllocationSettings {
+            rules: vec![
+                rule("mp_storage", None, AllocationDriver::VolumeShare),
+                rule("mp_storage", Some("cab-1"), AllocationDriver::UnitsStored),
+            ],
+        };
+        assert!(ok.validate().is_ok());
+
+        let unknown = StorageAllocationSettings {
+            rules: vec![rule("mp_logistics", None, AllocationDriver::UnitsStored)],
+        };
+        assert!(unknown.validate().is_err());
+
+        let duplicate = StorageAllocationSettings {
+            rules: vec![
+                rule("acceptance", Some("cab-1"), AllocationDriver::UnitsStored),
+                rule("acceptance", Some("cab-1"), AllocationDriver::RevenueShare),
+            ],
+        };
+        assert!(duplicate.validate().is_err());
+    }
+}
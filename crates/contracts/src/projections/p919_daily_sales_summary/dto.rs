@@ -0,0 +1,46 @@
+//! DTO проекции `p919_daily_sales_summary` — дневная сводка продаж по
+//! маркетплейсу и организации.
+//!
+//! Сводка складывается из проведённых документов продаж WB (a012) и транзакций
+//! OZON (a014): каждый документ вносит свой вклад в день, и при проведении или
+//! отмене проведения итог дня меняется на разницу вкладов, а не пересчитывается.
+
+use serde::{Deserialize, Serialize};
+
+/// Период графика по умолчанию, дней.
+pub const DEFAULT_PERIOD_DAYS: i64 = 90;
+
+/// Итоги дня по маркетплейсу и организации.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DailySalesSummaryDto {
+    /// Дата продажи (YYYY-MM-DD)
+    pub date: String,
+    /// Код маркетплейса: `WB`, `OZON`
+    pub marketplace: String,
+    pub organization_ref: String,
+    /// Число заказов (документов продажи без возвратов)
+    pub orders_count: i64,
+    /// Штуки: продажи минус возвраты
+    pub units: f64,
+    /// Выручка от покупателей за вычетом возвратов
+    pub gross: f64,
+    /// Комиссия маркетплейса (отрицательная)
+    pub commission: f64,
+    /// Итог к перечислению по данным p904
+    pub net: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DailySalesSummaryResponse {
+    pub date_from: String,
+    pub date_to: String,
+    pub items: Vec<DailySalesSummaryDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DailySalesSummaryRebuildResponse {
+    /// Документов, вошедших в сводку
+    pub documents: u64,
+    /// Строк сводки (день × маркетплейс × организация)
+    pub rows: u64,
+}
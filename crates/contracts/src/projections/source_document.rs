@@ -0,0 +1,38 @@
+//! Документ-источник строк проекций P900/P904.
+//!
+//! P900 (`document_type`) и P904 (`registrator_type`) хранят тип регистратора
+//! в историческом виде (`WB_Sales`, `OZON_FBS_Posting`, `OZON_FBS` …), а
+//! `registrator_ref` — id документа агрегата. Здесь — сопоставление такого
+//! типа с кодом агрегата: по нему фронтенд открывает карточку документа,
+//! а контроль качества проверяет, что документ ещё существует.
+
+/// Код агрегата документа-источника для исторического типа регистратора.
+/// `None` — тип не сопоставлен ни с одним агрегатом.
+pub fn source_aggregate(document_type: &str) -> Option<&'static str> {
+    match document_type {
+        "WB_Sales" => Some("a012_wb_sales"),
+        "YM_Order" => Some("a013_ym_order"),
+        "YM_Returns" => Some("a016_ym_returns"),
+        "OZON_FBS_Posting" | "OZON_FBS" => Some("a010_ozon_fbs_posting"),
+        "OZON_FBO_Posting" | "OZON_FBO" => Some("a011_ozon_fbo_posting"),
+        "OZON_Returns" => Some("a009_ozon_returns"),
+        "OZON_Transactions" => Some("a014_ozon_transactions"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_p900_and_p904_spellings_to_same_aggregate() {
+        assert_eq!(
+            source_aggregate("OZON_FBS_Posting"),
+            source_aggregate("OZON_FBS")
+        );
+        assert_eq!(source_aggregate("OZON_FBO"), Some("a011_ozon_fbo_posting"));
+        assert_eq!(source_aggregate("WB_Sales"), Some("a012_wb_sales"));
+        assert_eq!(source_aggregate("a012_wb_sales"), None);
+    }
+}
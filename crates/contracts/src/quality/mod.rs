@@ -279,3 +279,17 @@ pub struct NipCleanupResult {
     pub deleted_rows: usize,
     pub errors: Vec<String>,
 }
+
+/// Запрос принятия нарушений (расхождение признано допустимым).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViolationAcknowledgeRequest {
+    /// `ViolationItem::projection_id` принимаемых нарушений
+    pub ids: Vec<String>,
+}
+
+/// Результат принятия нарушений.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViolationAcknowledgeResult {
+    pub requested: usize,
+    pub acknowledged: usize,
+}
@@ -67,3 +67,33 @@ pub struct ScopeAccess {
     /// "read" or "all"
     pub mode: String,
 }
+
+// ============================================================================
+// Field-level access
+// ============================================================================
+
+/// Scope доступа к финансовым показателям документов (суммы, выплаты, маржа).
+/// Проверяется не маршрутом, а при формировании DTO: без него суммы не отдаются.
+pub const VIEW_FINANCE_SCOPE: &str = "view_finance";
+
+/// DTO с финансовыми полями, которые скрываются от ролей без [`VIEW_FINANCE_SCOPE`].
+pub trait FinanceFields {
+    /// Имена финансовых полей — как в JSON, ключах сортировки и колонках списка.
+    const FINANCE_FIELDS: &'static [&'static str];
+
+    /// Стирает финансовые значения: `Option` — в `None`, обязательные суммы — в ноль.
+    fn mask_finance(&mut self);
+}
+
+/// Видит ли пользователь финансовые поля (админ видит всегда).
+pub fn can_view_finance(is_admin: bool, scopes: &[ScopeAccess]) -> bool {
+    is_admin || scopes.iter().any(|s| s.scope_id == VIEW_FINANCE_SCOPE)
+}
+
+// ============================================================================
+// Action-level access
+// ============================================================================
+
+/// Scope утверждения решений по возвратам (на склад, утилизация, поставщику).
+/// Проверяется в хендлере утверждения: маршрут доступен всем авторизованным.
+pub const APPROVE_RETURN_DISPOSITION_SCOPE: &str = "approve_return_disposition";
@@ -0,0 +1,101 @@
+//! Формулы маржинальности единицы товара.
+//!
+//! Общий источник для расчёта плановой маржи `margin_pro` при проведении
+//! заказов (a015) и для сценарного калькулятора D409: расчёт «что если»
+//! обязан давать ту же маржу, что и проекции, при тех же входных данных.
+
+use serde::{Deserialize, Serialize};
+
+/// Цена за вычетом процентов комиссии (П1) и эквайринга (П2) маркетплейса.
+pub fn net_price(price: f64, commission_percent: f64, acquiring_percent: f64) -> f64 {
+    price * (100.0 - commission_percent - acquiring_percent) / 100.0
+}
+
+/// Наценка на себестоимость в процентах: `(выручка - себестоимость) / себестоимость * 100`.
+/// `None`, если себестоимость не положительна.
+pub fn markup_percent(net_revenue: f64, cost: f64) -> Option<f64> {
+    (cost > 0.0).then(|| (net_revenue - cost) / cost * 100.0)
+}
+
+/// Плановая маржа заказа `margin_pro`, %:
+/// `(base_price * (100 - П1 - П2) / 100 - dealer_price) / dealer_price * 100`.
+/// `None`, если цена или дилерская цена не положительны.
+pub fn margin_pro(
+    base_price: f64,
+    commission_percent: f64,
+    acquiring_percent: f64,
+    dealer_price: f64,
+) -> Option<f64> {
+    if base_price <= 0.0 {
+        return None;
+    }
+    markup_percent(
+        net_price(base_price, commission_percent, acquiring_percent),
+        dealer_price,
+    )
+}
+
+/// Экономика единицы товара: цена и удержания на одну проданную штуку.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct UnitEconomics {
+    pub price: f64,
+    pub commission_percent: f64,
+    pub acquiring_percent: f64,
+    pub logistics_per_unit: f64,
+    pub cost_per_unit: f64,
+    /// Хранение и приёмка WB на единицу — доля, распределённая на SKU (p918).
+    #[serde(default)]
+    pub storage_per_unit: f64,
+}
+
+impl UnitEconomics {
+    /// Прибыль с единицы: цена за вычетом комиссии, эквайринга, логистики,
+    /// хранения и себестоимости.
+    pub fn profit_per_unit(&self) -> f64 {
+        net_price(self.price, self.commission_percent, self.acquiring_percent)
+            - self.logistics_per_unit
+            - self.storage_per_unit
+            - self.cost_per_unit
+    }
+
+    /// Наценка с учётом логистики и хранения. Без них совпадает с [`margin_pro`].
+    pub fn markup_percent(&self) -> Option<f64> {
+        markup_percent(
+            self.profit_per_unit() + self.cost_per_unit,
+            self.cost_per_unit,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn margin_pro_matches_order_formula() {
+        // 1000 * (100 - 15 - 2) / 100 = 830; (830 - 500) / 500 * 100 = 66.
+        assert_eq!(margin_pro(1000.0, 15.0, 2.0, 500.0), Some(66.0));
+        assert_eq!(margin_pro(0.0, 15.0, 2.0, 500.0), None);
+        assert_eq!(margin_pro(1000.0, 15.0, 2.0, 0.0), None);
+    }
+
+    #[test]
+    fn unit_markup_without_logistics_equals_margin_pro() {
+        let unit = UnitEconomics {
+            price: 1000.0,
+            commission_percent: 15.0,
+            acquiring_percent: 2.0,
+            logistics_per_unit: 0.0,
+            cost_per_unit: 500.0,
+            storage_per_unit: 0.0,
+        };
+        assert_eq!(unit.markup_percent(), margin_pro(1000.0, 15.0, 2.0, 500.0));
+
+        let with_logistics = UnitEconomics {
+            logistics_per_unit: 80.0,
+            ..unit
+        };
+        assert_eq!(with_logistics.profit_per_unit(), 250.0);
+        assert_eq!(with_logistics.markup_percent(), Some(50.0));
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub mod margin;
 pub mod turnover;
 
 pub use turnover::*;
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Единый JSON-конверт ошибки API (тело любого не-2xx ответа backend `AppError`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiErrorBody {
+    /// Машиночитаемый код: `bad_request`, `forbidden`, `not_found`, `conflict`, `internal`
+    pub code: String,
+    /// Сообщение для пользователя
+    pub message: String,
+    /// Подробности: причина внутренней ошибки или данные конфликта
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+    /// Correlation id запроса (`x-request-id`) для поиска в журнале сервера
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
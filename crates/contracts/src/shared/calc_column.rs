@@ -0,0 +1,335 @@
+//! Вычисляемые колонки списков: `Маржа = amount_line - finished_price`.
+//!
+//! Пользователь задаёт в настройках списка имя колонки и выражение — арифметику
+//! `+ - * /` со скобками над числовыми полями списка и константами. Разбор общий:
+//! фронтенд проверяет выражение при вводе, бэкенд переводит его в SQL
+//! (`backend::shared::calc_column`) и отдаёт значения рядом со строками списка
+//! и в выгрузке. Деление на ноль и пустые поля дают пустое значение.
+//!
+//! В параметре запроса `calc` колонки перечисляются через `;`, имя от выражения
+//! отделяется `=`: `calc=Маржа=amount_line-finished_price;Доля=amount_line/total_price`.
+
+use serde::{Deserialize, Serialize};
+
+/// Больше колонок не принимаем: каждая — отдельное выражение в SELECT.
+pub const MAX_CALC_COLUMNS: usize = 5;
+const MAX_EXPR_LEN: usize = 200;
+
+/// Числовое поле, доступное в выражениях вычисляемых колонок списка.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalcField {
+    pub key: &'static str,
+    pub label: &'static str,
+}
+
+/// Определение колонки в настройках формы (как ввёл пользователь).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalcColumnDef {
+    pub name: String,
+    pub expr: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalcOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl CalcOp {
+    pub fn symbol(self) -> char {
+        match self {
+            Self::Add => '+',
+            Self::Sub => '-',
+            Self::Mul => '*',
+            Self::Div => '/',
+        }
+    }
+}
+
+/// Разобранное выражение. Поля — ключи из описания списка.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalcExpr {
+    Number(f64),
+    Field(&'static str),
+    Neg(Box<CalcExpr>),
+    Binary(Box<CalcExpr>, CalcOp, Box<CalcExpr>),
+}
+
+/// Проверенная колонка: определение и разобранное выражение.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalcColumn {
+    pub def: CalcColumnDef,
+    pub expr: CalcExpr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(CalcOp),
+    Open,
+    Close,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '+' | '-' | '*' | '/' => {
+                tokens.push(Token::Op(match c {
+                    '+' => CalcOp::Add,
+                    '-' => CalcOp::Sub,
+                    '*' => CalcOp::Mul,
+                    _ => CalcOp::Div,
+                }));
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::Open);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::Close);
+                i += 1;
+            }
+            c if c.is_ascii_digit() => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_ascii_digit() || matches!(chars[i], '.' | ','))
+                {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let value = text
+                    .replace(',', ".")
+                    .parse::<f64>()
+                    .map_err(|_| format!("Некорректное число «{}»", text))?;
+                tokens.push(Token::Number(value));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            c => return Err(format!("Недопустимый символ «{}»", c)),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Рекурсивный спуск: `expr = term {(+|-) term}`, `term = factor {(*|/) factor}`,
+/// `factor = -factor | число | поле | (expr)`.
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    fields: &'a [CalcField],
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expr(&mut self) -> Result<CalcExpr, String> {
+        let mut left = self.term()?;
+        while let Some(Token::Op(op @ (CalcOp::Add | CalcOp::Sub))) = self.peek().cloned() {
+            self.pos += 1;
+            left = CalcExpr::Binary(Box::new(left), op, Box::new(self.term()?));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<CalcExpr, String> {
+        let mut left = self.factor()?;
+        while let Some(Token::Op(op @ (CalcOp::Mul | CalcOp::Div))) = self.peek().cloned() {
+            self.pos += 1;
+            left = CalcExpr::Binary(Box::new(left), op, Box::new(self.factor()?));
+        }
+        Ok(left)
+    }
+
+    fn factor(&mut self) -> Result<CalcExpr, String> {
+        match self.next() {
+            Some(Token::Op(CalcOp::Sub)) => Ok(CalcExpr::Neg(Box::new(self.factor()?))),
+            Some(Token::Number(n)) => Ok(CalcExpr::Number(n)),
+            Some(Token::Ident(name)) => self
+                .fields
+                .iter()
+                .find(|f| f.key == name)
+                .map(|f| CalcExpr::Field(f.key))
+                .ok_or_else(|| format!("Неизвестное поле «{}»", name)),
+            Some(Token::Open) => {
+                let inner = self.expr()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => Err("Не закрыта скобка".to_string()),
+                }
+            }
+            Some(Token::Op(op)) => Err(format!("Лишний оператор «{}»", op.symbol())),
+            Some(Token::Close) => Err("Лишняя закрывающая скобка".to_string()),
+            None => Err("Выражение обрывается".to_string()),
+        }
+    }
+}
+
+/// Разбирает выражение над полями `fields`.
+pub fn parse_expr(src: &str, fields: &[CalcField]) -> Result<CalcExpr, String> {
+    if src.trim().is_empty() {
+        return Err("Пустое выражение".to_string());
+    }
+    if src.chars().count() > MAX_EXPR_LEN {
+        return Err(format!("Выражение длиннее {} символов", MAX_EXPR_LEN));
+    }
+    let mut parser = Parser {
+        tokens: tokenize(src)?,
+        pos: 0,
+        fields,
+    };
+    let expr = parser.expr()?;
+    if parser.pos < parser.tokens.len() {
+        return Err("Лишние символы после выражения".to_string());
+    }
+    Ok(expr)
+}
+
+/// Проверяет определения колонок: имя, выражение, лимит и уникальность имён.
+pub fn validate_columns(
+    defs: &[CalcColumnDef],
+    fields: &[CalcField],
+) -> Result<Vec<CalcColumn>, String> {
+    if defs.len() > MAX_CALC_COLUMNS {
+        return Err(format!(
+            "Не больше {} вычисляемых колонок",
+            MAX_CALC_COLUMNS
+        ));
+    }
+    let mut columns: Vec<CalcColumn> = Vec::with_capacity(defs.len());
+    for def in defs {
+        let name = def.name.trim();
+        if name.is_empty() {
+            return Err("У вычисляемой колонки нет имени".to_string());
+        }
+        if name.contains(['=', ';']) {
+            return Err(format!("Имя «{}» не должно содержать «=» и «;»", name));
+        }
+        if columns.iter().any(|c| c.def.name == name) {
+            return Err(format!("Колонка «{}» задана дважды", name));
+        }
+        let expr = parse_expr(&def.expr, fields).map_err(|e| format!("«{}»: {}", name, e))?;
+        columns.push(CalcColumn {
+            def: CalcColumnDef {
+                name: name.to_string(),
+                expr: def.expr.trim().to_string(),
+            },
+            expr,
+        });
+    }
+    Ok(columns)
+}
+
+/// Значение параметра `calc` для списка определений.
+pub fn to_param(defs: &[CalcColumnDef]) -> String {
+    defs.iter()
+        .map(|d| format!("{}={}", d.name.trim(), d.expr.trim()))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Разбирает параметр `calc` и проверяет колонки.
+pub fn parse_param(raw: &str, fields: &[CalcField]) -> Result<Vec<CalcColumn>, String> {
+    let defs = raw
+        .split(';')
+        .filter(|part| !part.trim().is_empty())
+        .map(|part| {
+            part.split_once('=')
+                .map(|(name, expr)| CalcColumnDef {
+                    name: name.to_string(),
+                    expr: expr.to_string(),
+                })
+                .ok_or_else(|| format!("Ожидается «имя=выражение»: «{}»", part.trim()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    validate_columns(&defs, fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIELDS: &[CalcField] = &[
+        CalcField {
+            key: "amount_line",
+            label: "К выплате",
+        },
+        CalcField {
+            key: "finished_price",
+            label: "Итоговая цена",
+        },
+        CalcField {
+            key: "qty",
+            label: "Количество",
+        },
+    ];
+
+    #[test]
+    fn parses_precedence_unary_and_parens() {
+        let e = parse_expr("amount_line - finished_price * 2", FIELDS).unwrap();
+        assert_eq!(
+            e,
+            CalcExpr::Binary(
+                Box::new(CalcExpr::Field("amount_line")),
+                CalcOp::Sub,
+                Box::new(CalcExpr::Binary(
+                    Box::new(CalcExpr::Field("finished_price")),
+                    CalcOp::Mul,
+                    Box::new(CalcExpr::Number(2.0)),
+                )),
+            )
+        );
+        assert!(parse_expr("-(amount_line + 0,5) / qty", FIELDS).is_ok());
+        assert!(parse_expr("amount_line -", FIELDS).is_err());
+        assert!(parse_expr("(amount_line", FIELDS).is_err());
+        assert!(parse_expr("amount_line)", FIELDS).is_err());
+        assert!(parse_expr("price * 2", FIELDS).is_err());
+        assert!(parse_expr("qty; DROP", FIELDS).is_err());
+    }
+
+    #[test]
+    fn param_roundtrip_and_limits() {
+        let defs = vec![
+            CalcColumnDef {
+                name: "Маржа".to_string(),
+                expr: "amount_line-finished_price".to_string(),
+            },
+            CalcColumnDef {
+                name: "Цена ед.".to_string(),
+                expr: "amount_line / qty".to_string(),
+            },
+        ];
+        let cols = parse_param(&to_param(&defs), FIELDS).unwrap();
+        assert_eq!(cols.len(), 2);
+        assert_eq!(cols[1].def.name, "Цена ед.");
+
+        assert!(parse_param("Маржа", FIELDS).is_err());
+        assert!(parse_param("a=qty;a=qty", FIELDS).is_err());
+        let many = (0..=MAX_CALC_COLUMNS)
+            .map(|i| format!("c{}=qty", i))
+            .collect::<Vec<_>>()
+            .join(";");
+        assert!(parse_param(&many, FIELDS).is_err());
+    }
+}
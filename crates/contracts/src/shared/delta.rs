@@ -0,0 +1,22 @@
+//! Дифференциальное обновление списков (delta refresh).
+//!
+//! Список, открытый во вкладке, после первой загрузки запрашивает только
+//! изменения: `?updated_after=<server_time прошлого ответа>` (или заголовок
+//! `If-Modified-Since`). Сервер отвечает `304 Not Modified`, если ничего не
+//! менялось, иначе — изменённые строки текущей выборки и id удалённых.
+
+use serde::{Deserialize, Serialize};
+
+/// Больше строк дельта не возвращает: при `truncated` клиент перезагружает страницу.
+pub const DELTA_MAX_ROWS: usize = 1000;
+
+/// Часть ответа списка, присутствующая только в режиме дельты.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaInfo {
+    /// Документы, помеченные удалёнными после `updated_after`.
+    #[serde(default)]
+    pub deleted_ids: Vec<String>,
+    /// Изменённых строк больше [`DELTA_MAX_ROWS`].
+    #[serde(default)]
+    pub truncated: bool,
+}
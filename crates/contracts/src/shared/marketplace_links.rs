@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Ссылка «открыть на маркетплейсе» для заголовка карточки документа.
+/// URL формирует backend (`shared::marketplaces::links`), фронтенд только выводит кнопки.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarketplaceLinkDto {
+    /// Подпись кнопки
+    pub title: String,
+    pub url: String,
+}
+
+/// Ответ детального эндпоинта: сам документ (поля на верхнем уровне, как раньше)
+/// плюс вычисленные ссылки на маркетплейс.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithMarketplaceLinks<T> {
+    #[serde(flatten)]
+    pub item: T,
+    #[serde(default)]
+    pub marketplace_links: Vec<MarketplaceLinkDto>,
+}
@@ -1,10 +1,15 @@
 pub mod access;
 pub mod accounting;
 pub mod analytics;
+pub mod api_error;
 pub mod bi_timeline;
+pub mod calc_column;
 pub mod data_view;
+pub mod delta;
 pub mod drilldown;
 pub mod form_settings;
 pub mod logger;
+pub mod marketplace_links;
 pub mod metadata;
+pub mod quick_filter;
 pub mod universal_dashboard;
@@ -0,0 +1,351 @@
+//! Мини-язык быстрых фильтров списков: `status:unposted warehouse:"Коледино" amount>1000`.
+//!
+//! Строка разбирается на термы `поле<оп>значение`; слова без поля — свободный поиск
+//! (как обычная строка поиска списка). Разбор общий: фронтенд подсказывает поля и
+//! проверяет строку до запроса, бэкенд превращает термы в условия WHERE по описанию
+//! полей конкретного списка (`backend::shared::quick_filter`).
+//!
+//! Операторы: `:` (содержит / равно для чисел и перечислений), `=`, `!=`, `>`, `>=`, `<`, `<=`.
+//! Значение с пробелами берётся в двойные кавычки.
+
+/// Оператор терма.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickFilterOp {
+    /// `:` — текст содержит значение; для чисел, дат и перечислений — равенство.
+    Match,
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl QuickFilterOp {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Match => ":",
+            Self::Eq => "=",
+            Self::Ne => "!=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+        }
+    }
+
+    fn is_comparison(self) -> bool {
+        matches!(self, Self::Gt | Self::Ge | Self::Lt | Self::Le)
+    }
+}
+
+/// Операторы в порядке поиска: двухсимвольные раньше односимвольных.
+const OPS: &[(&str, QuickFilterOp)] = &[
+    ("!=", QuickFilterOp::Ne),
+    (">=", QuickFilterOp::Ge),
+    ("<=", QuickFilterOp::Le),
+    (":", QuickFilterOp::Match),
+    ("=", QuickFilterOp::Eq),
+    (">", QuickFilterOp::Gt),
+    ("<", QuickFilterOp::Lt),
+];
+
+/// Терм строки фильтра. `field == None` — слово свободного поиска.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickFilterTerm {
+    pub field: Option<String>,
+    pub op: QuickFilterOp,
+    pub value: String,
+}
+
+/// Тип значения поля — определяет допустимые операторы и формат значения.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickFilterKind {
+    Text,
+    Number,
+    /// `YYYY-MM-DD`; для `:` допускается месяц `YYYY-MM`.
+    Date,
+    /// Одно из фиксированных значений.
+    Enum(&'static [&'static str]),
+}
+
+/// Поле, доступное в быстром фильтре списка.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuickFilterField {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub kind: QuickFilterKind,
+    /// Сумма или цена: без доступа к финансам отбор по полю отклоняется сервером,
+    /// иначе значения угадываются сужением диапазона.
+    pub finance: bool,
+}
+
+/// Разбитая строка фильтра: термы по полям и слова свободного поиска.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuickFilterQuery {
+    pub terms: Vec<QuickFilterTerm>,
+    pub free_text: Vec<String>,
+}
+
+/// Делит строку на токены по пробелам, не разрывая значения в кавычках.
+fn tokenize(input: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for ch in input.chars() {
+        match ch {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(ch);
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("Незакрытая кавычка".to_string());
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+fn unquote(value: &str) -> String {
+    value.replace('"', "")
+}
+
+/// Разбирает токен `поле<оп>значение`; `None` — токен не похож на терм.
+fn split_term(token: &str) -> Option<(&str, QuickFilterOp, &str)> {
+    let key_len = token
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(token.len());
+    if key_len == 0 {
+        return None;
+    }
+    let rest = &token[key_len..];
+    OPS.iter()
+        .find(|(sym, _)| rest.starts_with(sym))
+        .map(|(sym, op)| (&token[..key_len], *op, &rest[sym.len()..]))
+}
+
+/// Разбирает строку без проверки полей.
+pub fn parse(input: &str) -> Result<QuickFilterQuery, String> {
+    let mut query = QuickFilterQuery::default();
+    for token in tokenize(input)? {
+        match split_term(&token) {
+            Some((key, op, raw_value)) => {
+                let value = unquote(raw_value);
+                if value.is_empty() {
+                    return Err(format!("Пустое значение в «{}»", token));
+                }
+                query.terms.push(QuickFilterTerm {
+                    field: Some(key.to_lowercase()),
+                    op,
+                    value,
+                });
+            }
+            None => query.free_text.push(unquote(&token)),
+        }
+    }
+    Ok(query)
+}
+
+fn is_date(value: &str, allow_month: bool) -> bool {
+    let shape_ok = |s: &str, len: usize| {
+        s.len() == len
+            && s.char_indices().all(|(i, c)| {
+                if i == 4 || i == 7 {
+                    c == '-'
+                } else {
+                    c.is_ascii_digit()
+                }
+            })
+    };
+    shape_ok(value, 10) || (allow_month && shape_ok(value, 7))
+}
+
+/// Проверяет терм по описанию поля: оператор и формат значения.
+fn check_term(field: &QuickFilterField, term: &QuickFilterTerm) -> Result<(), String> {
+    match field.kind {
+        QuickFilterKind::Text => {
+            if term.op.is_comparison() {
+                return Err(format!(
+                    "Поле «{}» текстовое: используйте «:», «=» или «!=»",
+                    field.key
+                ));
+            }
+        }
+        QuickFilterKind::Number => {
+            if term.value.replace(',', ".").parse::<f64>().is_err() {
+                return Err(format!(
+                    "Поле «{}» ожидает число, получено «{}»",
+                    field.key, term.value
+                ));
+            }
+        }
+        QuickFilterKind::Date => {
+            if !is_date(&term.value, term.op == QuickFilterOp::Match) {
+                return Err(format!(
+                    "Поле «{}» ожидает дату ГГГГ-ММ-ДД, получено «{}»",
+                    field.key, term.value
+                ));
+            }
+        }
+        QuickFilterKind::Enum(values) => {
+            if term.op.is_comparison() {
+                return Err(format!(
+                    "Поле «{}» поддерживает только «:» и «!=»",
+                    field.key
+                ));
+            }
+            if !values.iter().any(|v| v.eq_ignore_ascii_case(&term.value)) {
+                return Err(format!(
+                    "Поле «{}»: допустимые значения {}",
+                    field.key,
+                    values.join(", ")
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Разбирает строку и проверяет термы по полям списка.
+pub fn parse_for(fields: &[QuickFilterField], input: &str) -> Result<QuickFilterQuery, String> {
+    let query = parse(input)?;
+    for term in &query.terms {
+        let key = term.field.as_deref().unwrap_or_default();
+        let field = fields.iter().find(|f| f.key == key).ok_or_else(|| {
+            format!(
+                "Неизвестное поле «{}». Доступны: {}",
+                key,
+                fields.iter().map(|f| f.key).collect::<Vec<_>>().join(", ")
+            )
+        })?;
+        check_term(field, term)?;
+    }
+    Ok(query)
+}
+
+/// Подсказка автодополнения: строка целиком после выбора подсказки.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickFilterSuggestion {
+    pub replacement: String,
+    pub label: String,
+}
+
+/// Подсказки для последнего (редактируемого) токена строки:
+/// имена полей, а после `поле:` — значения перечислений.
+pub fn suggest(fields: &[QuickFilterField], input: &str) -> Vec<QuickFilterSuggestion> {
+    let quotes_open = input.matches('"').count() % 2 == 1;
+    if quotes_open {
+        return Vec::new();
+    }
+    let start = input
+        .rfind(char::is_whitespace)
+        .map(|i| i + input[i..].chars().next().map_or(1, char::len_utf8))
+        .unwrap_or(0);
+    let (head, token) = input.split_at(start);
+
+    if let Some((key, op, partial)) = split_term(token) {
+        let Some(field) = fields.iter().find(|f| f.key == key.to_lowercase()) else {
+            return Vec::new();
+        };
+        let QuickFilterKind::Enum(values) = field.kind else {
+            return Vec::new();
+        };
+        return values
+            .iter()
+            .filter(|v| v.starts_with(&partial.to_lowercase()) && **v != partial)
+            .map(|v| QuickFilterSuggestion {
+                replacement: format!("{}{}{}{} ", head, key, op.symbol(), v),
+                label: format!("{}{}{}", key, op.symbol(), v),
+            })
+            .collect();
+    }
+
+    let partial = token.to_lowercase();
+    fields
+        .iter()
+        .filter(|f| f.key.starts_with(&partial))
+        .map(|f| QuickFilterSuggestion {
+            replacement: format!("{}{}:", head, f.key),
+            label: format!("{}: — {}", f.key, f.label),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIELDS: &[QuickFilterField] = &[
+        QuickFilterField {
+            key: "status",
+            label: "Статус",
+            kind: QuickFilterKind::Enum(&["posted", "unposted"]),
+            finance: false,
+        },
+        QuickFilterField {
+            key: "warehouse",
+            label: "Склад",
+            kind: QuickFilterKind::Text,
+            finance: false,
+        },
+        QuickFilterField {
+            key: "amount",
+            label: "Сумма",
+            kind: QuickFilterKind::Number,
+            finance: true,
+        },
+        QuickFilterField {
+            key: "date",
+            label: "Дата",
+            kind: QuickFilterKind::Date,
+            finance: false,
+        },
+    ];
+
+    #[test]
+    fn parses_terms_quotes_and_free_text() {
+        let q = parse_for(
+            FIELDS,
+            r#"status:unposted warehouse:"Коледино 2" amount>=1000 ТВИЗ"#,
+        )
+        .unwrap();
+        assert_eq!(q.free_text, vec!["ТВИЗ".to_string()]);
+        assert_eq!(q.terms.len(), 3);
+        assert_eq!(q.terms[1].value, "Коледино 2");
+        assert_eq!(q.terms[2].op, QuickFilterOp::Ge);
+        assert_eq!(q.terms[2].value, "1000");
+    }
+
+    #[test]
+    fn rejects_bad_terms() {
+        assert!(parse_for(FIELDS, "foo:bar").is_err());
+        assert!(parse_for(FIELDS, "amount>abc").is_err());
+        assert!(parse_for(FIELDS, "warehouse>5").is_err());
+        assert!(parse_for(FIELDS, "status:draft").is_err());
+        assert!(parse_for(FIELDS, r#"warehouse:"Коледино"#).is_err());
+        assert!(parse_for(FIELDS, "date:2025-01").is_ok());
+        assert!(parse_for(FIELDS, "date>2025-01").is_err());
+    }
+
+    #[test]
+    fn suggests_fields_and_enum_values() {
+        let s = suggest(FIELDS, "amount>10 st");
+        assert_eq!(s.len(), 1);
+        assert_eq!(s[0].replacement, "amount>10 status:");
+
+        let s = suggest(FIELDS, "status:u");
+        assert_eq!(s.len(), 1);
+        assert_eq!(s[0].replacement, "status:unposted ");
+
+        assert!(suggest(FIELDS, "warehouse:Ко").is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+//! Объявления «Что нового»: администратор публикует заметки о релизах и новых
+//! инструментах, пользователи видят их в панели в шапке с отметкой о прочтении.
+
+use serde::{Deserialize, Serialize};
+
+/// Максимальная длина заголовка объявления (символов).
+pub const ANNOUNCEMENT_TITLE_MAX_LEN: usize = 200;
+
+/// Объявление в списке администратора (включая черновики).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AnnouncementDto {
+    pub id: String,
+    pub title: String,
+    /// Текст; переносы строк сохраняются при показе
+    pub body: String,
+    pub version: Option<String>,
+    /// Ключ вкладки, которую открыть по клику
+    pub tab_key: Option<String>,
+    /// `None` — черновик, пользователям не показывается
+    pub published_at: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Создание и изменение объявления.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AnnouncementUpsertRequest {
+    pub title: String,
+    pub body: String,
+    pub version: Option<String>,
+    pub tab_key: Option<String>,
+    /// Опубликовать (при снятии флага объявление возвращается в черновики)
+    pub published: bool,
+}
+
+/// Опубликованное объявление в ленте пользователя.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AnnouncementFeedItem {
+    pub id: String,
+    pub title: String,
+    pub body: String,
+    pub version: Option<String>,
+    pub tab_key: Option<String>,
+    pub published_at: String,
+    pub read: bool,
+}
+
+/// Лента «Что нового» текущего пользователя.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AnnouncementFeedDto {
+    pub items: Vec<AnnouncementFeedItem>,
+    pub unread: i64,
+}
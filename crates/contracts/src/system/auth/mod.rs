@@ -32,7 +32,7 @@ pub struct UserInfo {
     pub full_name: Option<String>,
     pub email: Option<String>,
     pub is_admin: bool,
-    /// Primary role code (e.g. "admin", "manager", "operator", "viewer")
+    /// Primary role code (e.g. "admin", "manager", "operator", "viewer", "warehouse")
     #[serde(default = "default_viewer_role")]
     pub primary_role: String,
     /// Effective scope access list, resolved from primary + additional roles.
@@ -57,3 +57,143 @@ pub struct TokenClaims {
 fn default_viewer_role() -> String {
     "viewer".to_string()
 }
+
+// ============================================================================
+// Вход через корпоративный OIDC-провайдер (authorization code flow)
+// ============================================================================
+
+/// Сопоставление группы провайдера с ролью. Порядок в списке — приоритет:
+/// основная роль берётся из первого совпадения.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OidcRoleMapping {
+    pub group: String,
+    pub role_code: String,
+    /// Участники группы получают права администратора
+    #[serde(default)]
+    pub is_admin: bool,
+}
+
+/// Настройки OIDC для развёртывания (`sys_settings`, ключ `oidc_config`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OidcSettings {
+    pub enabled: bool,
+    /// Подпись кнопки на странице входа
+    pub display_name: String,
+    /// Issuer; discovery читается из `{issuer_url}/.well-known/openid-configuration`
+    pub issuer_url: String,
+    pub client_id: String,
+    /// В ответе API всегда пустой; пустой в запросе — оставить сохранённый
+    #[serde(default)]
+    pub client_secret: String,
+    #[serde(default)]
+    pub client_secret_set: bool,
+    /// Зарегистрированный у провайдера адрес `/api/system/auth/oidc/callback`
+    pub redirect_uri: String,
+    /// Адрес фронтенда, куда вернуть браузер после входа (пусто — корень сервера)
+    #[serde(default)]
+    pub post_login_url: String,
+    pub scopes: String,
+    /// Claim ID-токена со списком групп
+    pub groups_claim: String,
+    #[serde(default)]
+    pub role_mappings: Vec<OidcRoleMapping>,
+    /// Роль для пользователей без подходящей группы; `None` — вход запрещён
+    #[serde(default)]
+    pub default_role_code: Option<String>,
+    /// Создавать пользователя при первом входе
+    #[serde(default)]
+    pub auto_create_users: bool,
+}
+
+impl Default for OidcSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            display_name: "Корпоративный вход".to_string(),
+            issuer_url: String::new(),
+            client_id: String::new(),
+            client_secret: String::new(),
+            client_secret_set: false,
+            redirect_uri: String::new(),
+            post_login_url: String::new(),
+            scopes: "openid profile email".to_string(),
+            groups_claim: "groups".to_string(),
+            role_mappings: Vec::new(),
+            default_role_code: None,
+            auto_create_users: true,
+        }
+    }
+}
+
+/// Роль по группам пользователя: `(primary_role_code, is_admin)`.
+/// `None` — нет ни подходящей группы, ни роли по умолчанию.
+pub fn oidc_role_for_groups(settings: &OidcSettings, groups: &[String]) -> Option<(String, bool)> {
+    let matches = |m: &&OidcRoleMapping| groups.iter().any(|g| g.eq_ignore_ascii_case(&m.group));
+    let is_admin = settings
+        .role_mappings
+        .iter()
+        .filter(matches)
+        .any(|m| m.is_admin);
+    settings
+        .role_mappings
+        .iter()
+        .find(matches)
+        .map(|m| m.role_code.clone())
+        .or_else(|| settings.default_role_code.clone())
+        .map(|role| (role, is_admin))
+}
+
+/// Что нужно странице входа до авторизации.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OidcLoginInfo {
+    pub enabled: bool,
+    pub display_name: String,
+}
+
+/// Одноразовый код, который callback передаёт фронтенду в `?oidc_code=`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcExchangeRequest {
+    pub code: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(group: &str, role: &str, is_admin: bool) -> OidcRoleMapping {
+        OidcRoleMapping {
+            group: group.to_string(),
+            role_code: role.to_string(),
+            is_admin,
+        }
+    }
+
+    #[test]
+    fn maps_groups_to_roles_by_priority() {
+        let mut settings = OidcSettings {
+            role_mappings: vec![
+                mapping("it-admins", "admin", true),
+                mapping("sales", "manager", false),
+                mapping("all-staff", "viewer", false),
+            ],
+            ..OidcSettings::default()
+        };
+        let groups = |list: &[&str]| list.iter().map(|g| g.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            oidc_role_for_groups(&settings, &groups(&["all-staff", "Sales"])),
+            Some(("manager".to_string(), false))
+        );
+        assert_eq!(
+            oidc_role_for_groups(&settings, &groups(&["all-staff", "it-admins"])),
+            Some(("admin".to_string(), true))
+        );
+        assert_eq!(oidc_role_for_groups(&settings, &groups(&["guests"])), None);
+
+        settings.default_role_code = Some("viewer".to_string());
+        assert_eq!(
+            oidc_role_for_groups(&settings, &[]),
+            Some(("viewer".to_string(), false))
+        );
+    }
+}
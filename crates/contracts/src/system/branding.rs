@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+/// Название продукта без брендирования.
+pub const DEFAULT_PRODUCT_NAME: &str = "Integrator";
+
+/// Максимальная длина `logo_url` (data URI логотипа хранится прямо в строке).
+pub const MAX_LOGO_URL_LEN: usize = 256 * 1024;
+
+/// Брендирование для текущего хоста — публичный ответ, нужен ещё до входа.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BrandingDto {
+    /// `None` — брендирование не настроено, используется стандартный вид.
+    pub organization_id: Option<String>,
+    pub product_name: String,
+    /// Акцентный цвет `#rrggbb`, заменяет `--color-primary` темы.
+    pub accent_color: Option<String>,
+    pub logo_url: Option<String>,
+}
+
+impl Default for BrandingDto {
+    fn default() -> Self {
+        Self {
+            organization_id: None,
+            product_name: DEFAULT_PRODUCT_NAME.to_string(),
+            accent_color: None,
+            logo_url: None,
+        }
+    }
+}
+
+/// Настройки брендирования одной организации.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OrgBrandingSettings {
+    pub product_name: String,
+    pub accent_color: Option<String>,
+    pub logo_url: Option<String>,
+    /// Хосты (без порта), на которых показывается это брендирование.
+    pub hosts: Vec<String>,
+    /// Брендирование для хостов, не указанных ни у одной организации.
+    pub is_default: bool,
+}
+
+/// Организация и её брендирование (`settings == None` — не настроено).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OrgBrandingDto {
+    pub organization_id: String,
+    pub organization_name: String,
+    pub settings: Option<OrgBrandingSettings>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OrgBrandingUpsertRequest {
+    pub organization_id: String,
+    pub settings: OrgBrandingSettings,
+}
+
+/// Цвет в формате `#rrggbb`.
+pub fn is_valid_accent_color(value: &str) -> bool {
+    value.len() == 7 && value.starts_with('#') && value[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Хост для сравнения: нижний регистр, без порта и пробелов.
+pub fn normalize_host(value: &str) -> String {
+    let host = value.trim().to_lowercase();
+    match host.rsplit_once(':') {
+        Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name.to_string(),
+        _ => host,
+    }
+}
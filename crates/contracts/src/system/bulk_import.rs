@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// Ошибка массовой загрузки: строка файла, не прошедшая проверку, или пачка,
+/// которую не удалось записать целиком.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BulkImportErrorDto {
+    /// Номер пачки (с 0), в которую попала строка.
+    pub chunk: usize,
+    /// Номер строки файла (с 1); для ошибки записи — первая строка пачки.
+    pub line: usize,
+    pub message: String,
+}
+
+/// Итог загрузки NDJSON-файла.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BulkImportResultDto {
+    /// Непустых строк в файле.
+    pub total_lines: usize,
+    /// Строк записано (вставлено или обновлено по ключу).
+    pub upserted: usize,
+    /// Строк отброшено: ошибки разбора, проверки и записи пачек.
+    pub rejected: usize,
+    /// Пачек отправлено в БД.
+    pub chunks: usize,
+    /// Диапазон дат записанных строк `YYYY-MM-DD`.
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    /// Сколько дней перепроведено (если загрузка запрошена с `rebuild=true`).
+    pub rebuilt_days: Option<usize>,
+    pub errors: Vec<BulkImportErrorDto>,
+    /// Ошибок было больше, чем вернулось в `errors`.
+    pub errors_truncated: bool,
+}
@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+
+/// Окно отмены по умолчанию, минут.
+pub const BULK_UNDO_WINDOW_DEFAULT_MINUTES: i64 = 60;
+
+/// Максимально допустимое окно отмены (7 суток), минут.
+pub const BULK_UNDO_WINDOW_MAX_MINUTES: i64 = 7 * 24 * 60;
+
+/// Одна массовая операция из журнала `sys_bulk_operations`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BulkOperationDto {
+    pub id: String,
+    /// Вид операции: `unpost`.
+    pub op_kind: String,
+    /// Тип документов: `a012_wb_sales`, `a013_ym_order`, `a016_ym_returns`.
+    pub entity_type: String,
+    pub created_at: String,
+    pub created_by: Option<String>,
+    /// Сколько документов затронуто (и будет восстановлено отменой).
+    pub item_count: i64,
+    /// `done` | `undoing` | `undone`.
+    pub status: String,
+    pub undone_at: Option<String>,
+    pub undone_by: Option<String>,
+    pub undo_error: Option<String>,
+    /// Момент, после которого отмена недоступна (UTC ISO8601).
+    pub undo_deadline: String,
+    /// Отмена доступна сейчас: статус `done` и окно не истекло.
+    pub can_undo: bool,
+}
+
+/// Ответ списка операций для страницы «История операций».
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkOperationListResponse {
+    pub rows: Vec<BulkOperationDto>,
+    pub undo_window_minutes: i64,
+}
+
+/// Итог отмены операции (отмена всё-или-ничего).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkUndoResultDto {
+    pub restored: usize,
+    pub status: String,
+}
+
+/// Настройка окна отмены (GET/POST `/api/sys/bulk-operations/undo-window`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BulkUndoWindowDto {
+    pub minutes: i64,
+}
+
+/// Сколько идентификаторов принимается в одном вставленном списке.
+pub const BULK_LOOKUP_MAX_IDS: usize = 1000;
+
+/// Разбор списка, вставленного из буфера обмена: разделители — перевод строки,
+/// табуляция, запятая, точка с запятой; кавычки по краям снимаются (копия из Excel/CSV).
+/// Пустые строки и повторы отбрасываются, порядок первого вхождения сохраняется.
+pub fn parse_id_list(text: &str) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    text.split(['\n', '\r', '\t', ',', ';'])
+        .map(|s| s.trim().trim_matches(['"', '\'']).trim())
+        .filter(|s| !s.is_empty())
+        .filter(|s| seen.insert(s.to_string()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// POST `/api/sys/bulk-operations/lookup` — список SRID / номеров отправлений.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkLookupRequest {
+    pub ids: Vec<String>,
+}
+
+/// Документ, найденный по номеру из списка.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BulkLookupItemDto {
+    /// Строка из вставленного списка
+    pub input: String,
+    /// `a012_wb_sales` | `a015_wb_orders` | `a010_ozon_fbs_posting` | `a011_ozon_fbo_posting`
+    pub entity_type: String,
+    pub id: String,
+    pub document_no: String,
+    pub description: String,
+    pub is_posted: bool,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkLookupResponse {
+    pub items: Vec<BulkLookupItemDto>,
+    /// Строки списка, по которым документ не найден
+    pub not_found: Vec<String>,
+}
+
+/// Ссылка на документ в пакетном действии.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BulkDocumentRef {
+    pub entity_type: String,
+    pub id: String,
+}
+
+/// Итог пакетного действия над документами (результат пакетной операции).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BulkActionResultDto {
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Первые ошибки: `document_no: текст`
+    pub errors: Vec<String>,
+}
+
+/// Агрегаты, в которых можно переназначить организацию / маркетплейс.
+pub const REASSIGN_ENTITY_TYPES: &[&str] = &[
+    "a012_wb_sales",
+    "a013_ym_order",
+    "a015_wb_orders",
+    "a016_ym_returns",
+];
+
+/// Отбор документов для переназначения. Пустой отбор не принимается —
+/// нужен хотя бы один сужающий критерий.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BulkReassignFilter {
+    /// Конкретные документы (id)
+    #[serde(default)]
+    pub ids: Vec<String>,
+    /// Текущая организация документа
+    pub organization_id: Option<String>,
+    /// Текущий маркетплейс документа
+    pub marketplace_id: Option<String>,
+    /// Подключение, через которое документ загружен
+    pub connection_id: Option<String>,
+    /// Дата загрузки (`created_at`) с, `YYYY-MM-DD`
+    pub created_from: Option<String>,
+    /// Дата загрузки (`created_at`) по, `YYYY-MM-DD` включительно
+    pub created_to: Option<String>,
+}
+
+/// POST `/api/sys/bulk-operations/reassign` — исправление организации / маркетплейса
+/// в исторических документах. `dry_run` только считает, что изменится.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkReassignRequest {
+    pub entity_type: String,
+    pub filter: BulkReassignFilter,
+    pub set_organization_id: Option<String>,
+    pub set_marketplace_id: Option<String>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Документ из отчёта: значения до переназначения.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BulkReassignSampleDto {
+    pub id: String,
+    pub document_no: String,
+    pub organization_id: String,
+    pub marketplace_id: String,
+}
+
+/// Строки проекции, затронутые переназначением.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BulkReassignTableDto {
+    pub table: String,
+    pub rows: u64,
+}
+
+/// Отчёт переназначения (при `dry_run` — прогноз, изменения откатываются).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkReassignReportDto {
+    pub dry_run: bool,
+    pub entity_type: String,
+    /// Документов под отбором
+    pub matched: usize,
+    /// Документов, у которых значение изменится
+    pub documents_changed: usize,
+    pub projections: Vec<BulkReassignTableDto>,
+    /// Первые документы отбора
+    pub sample: Vec<BulkReassignSampleDto>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_id_list_splits_trims_and_dedups() {
+        let text = "  123abc\r\n\n\"456def\"\t789;123abc,  \n";
+        assert_eq!(parse_id_list(text), vec!["123abc", "456def", "789"]);
+    }
+}
@@ -0,0 +1,102 @@
+//! Пакет пользовательской конфигурации (JSON) для переноса между развёртываниями
+//! (staging → production) без повторной настройки вручную.
+//!
+//! В пакет попадают только настройки, заданные пользователями: представления форм,
+//! сохранённые дашборды, бизнес-настройки из белого списка, брендирование
+//! организаций, избранное и настройки уведомлений. Правила сопоставления и
+//! проведения задаются кодом и в пакет не входят; секреты (JWT, OIDC) и
+//! настройки конкретного сервера (планировщик, хосты брендирования) не переносятся.
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::form_settings::FormSettings;
+use crate::system::branding::OrgBrandingSettings;
+use crate::system::favorites::FavoriteUpsertRequest;
+use crate::system::notifications::NotificationPreference;
+
+/// Версия формата пакета; импорт пакета другой версии отклоняется.
+pub const CONFIG_BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Ключи `sys_settings`, которые переносятся пакетом.
+pub const CONFIG_BUNDLE_SETTING_KEYS: [&str; 2] =
+    ["p904_return_netting_mode", "bulk_undo_window_minutes"];
+
+/// Сохранённая конфигурация дашборда (`sys_dashboard_configs`), id сохраняется.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BundleDashboardConfig {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub data_source: String,
+    pub config_json: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BundleSetting {
+    pub key: String,
+    pub value: String,
+}
+
+/// Брендирование организации; хосты при импорте берутся из целевого развёртывания.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BundleOrgBranding {
+    pub organization_id: String,
+    pub settings: OrgBrandingSettings,
+}
+
+/// Избранное пользователя; владелец сопоставляется по `username`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BundleFavorite {
+    pub username: String,
+    pub favorite: FavoriteUpsertRequest,
+}
+
+/// Настройки уведомлений пользователя; сопоставляется по `username`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BundleNotificationPreferences {
+    pub username: String,
+    pub preferences: Vec<NotificationPreference>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub format_version: u32,
+    /// UTC RFC3339
+    pub exported_at: String,
+    #[serde(default)]
+    pub form_settings: Vec<FormSettings>,
+    #[serde(default)]
+    pub dashboard_configs: Vec<BundleDashboardConfig>,
+    #[serde(default)]
+    pub settings: Vec<BundleSetting>,
+    #[serde(default)]
+    pub org_branding: Vec<BundleOrgBranding>,
+    #[serde(default)]
+    pub favorites: Vec<BundleFavorite>,
+    #[serde(default)]
+    pub notification_preferences: Vec<BundleNotificationPreferences>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigImportRequest {
+    pub bundle: ConfigBundle,
+    /// Только проверить пакет и посчитать, что будет применено.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Итог импорта по разделу пакета.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConfigImportSection {
+    pub section: String,
+    pub applied: usize,
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConfigImportResult {
+    pub dry_run: bool,
+    pub sections: Vec<ConfigImportSection>,
+    /// Причины пропуска записей (неизвестный пользователь, организация и т.п.)
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,162 @@
+//! Свежесть данных: когда источник последний раз импортировался и не устарел ли
+//! он относительно SLA. Возвращается вместе с ответами списков и дашбордов и
+//! показывается бейджем «данные на 06:32», чтобы решения не принимались по
+//! устаревшим цифрам незаметно.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Источник данных с собственным временем последнего импорта.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FreshnessDataset {
+    WbSales,
+    WbOrders,
+    WbFinanceReport,
+    WbAdvert,
+    Ozon,
+    Yandex,
+}
+
+impl FreshnessDataset {
+    pub fn all() -> [Self; 6] {
+        [
+            Self::WbSales,
+            Self::WbOrders,
+            Self::WbFinanceReport,
+            Self::WbAdvert,
+            Self::Ozon,
+            Self::Yandex,
+        ]
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::WbSales => "wb_sales",
+            Self::WbOrders => "wb_orders",
+            Self::WbFinanceReport => "wb_finance_report",
+            Self::WbAdvert => "wb_advert",
+            Self::Ozon => "ozon",
+            Self::Yandex => "yandex",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|dataset| dataset.code() == code)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::WbSales => "WB продажи",
+            Self::WbOrders => "WB заказы",
+            Self::WbFinanceReport => "WB финансовый отчёт",
+            Self::WbAdvert => "WB реклама",
+            Self::Ozon => "Ozon",
+            Self::Yandex => "Яндекс Маркет",
+        }
+    }
+
+    /// SLA по умолчанию, часов: сколько данные могут не обновляться.
+    /// Финотчёт WB публикуется раз в неделю, поэтому окно больше.
+    pub fn default_sla_hours(&self) -> i64 {
+        match self {
+            Self::WbSales => 24,
+            Self::WbOrders => 6,
+            Self::WbFinanceReport => 8 * 24,
+            Self::WbAdvert => 48,
+            Self::Ozon => 24,
+            Self::Yandex => 24,
+        }
+    }
+
+    /// Типы регламентных заданий (`sys_tasks.task_type`), успешный запуск которых
+    /// обновляет источник.
+    pub fn task_types(&self) -> &'static [&'static str] {
+        match self {
+            Self::WbSales => &["task004_wb_sales"],
+            Self::WbOrders => &[
+                "task001_wb_orders_fbs_polling",
+                "task002_wb_orders_stats_hourly",
+            ],
+            Self::WbFinanceReport => &["task006_wb_finance"],
+            Self::WbAdvert => &["task011_wb_advert"],
+            Self::Ozon => &["u502_import_ozon"],
+            Self::Yandex => &["u503_import_yandex", "task013_ym_orders_polling"],
+        }
+    }
+
+    /// Агрегаты в журнале происхождения импорта (`sys_import_batches`): учитывают
+    /// и ручные загрузки, которые не пишут запусков заданий.
+    pub fn import_aggregates(&self) -> &'static [&'static str] {
+        match self {
+            Self::WbSales => &["a012_wb_sales"],
+            Self::WbOrders => &["a015_wb_orders"],
+            _ => &[],
+        }
+    }
+}
+
+/// Свежесть одного источника.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DataFreshnessDto {
+    pub dataset: FreshnessDataset,
+    pub label: String,
+    /// RFC3339; `None` — импортов ещё не было
+    pub last_import_at: Option<String>,
+    pub sla_hours: i64,
+    /// Последний импорт старше SLA (или его не было)
+    pub stale: bool,
+}
+
+/// Переопределения SLA по коду источника (`sys_settings`, JSON).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DataFreshnessSlaSettings {
+    #[serde(default)]
+    pub sla_hours: BTreeMap<String, i64>,
+}
+
+impl DataFreshnessSlaSettings {
+    /// Переопределение, если оно задано и положительно, иначе SLA по умолчанию.
+    pub fn sla_hours_for(&self, dataset: FreshnessDataset) -> i64 {
+        self.sla_hours
+            .get(dataset.code())
+            .copied()
+            .filter(|hours| *hours > 0)
+            .unwrap_or_else(|| dataset.default_sla_hours())
+    }
+}
+
+pub fn is_stale(last_import_at: Option<DateTime<Utc>>, sla_hours: i64, now: DateTime<Utc>) -> bool {
+    match last_import_at {
+        Some(at) => now - at > Duration::hours(sla_hours),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn staleness_is_measured_against_sla() {
+        let now = DateTime::parse_from_rfc3339("2026-10-18T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert!(!is_stale(Some(now - Duration::hours(5)), 6, now));
+        assert!(is_stale(Some(now - Duration::hours(7)), 6, now));
+        assert!(is_stale(None, 6, now));
+    }
+
+    #[test]
+    fn sla_override_falls_back_to_default() {
+        let mut settings = DataFreshnessSlaSettings::default();
+        settings.sla_hours.insert("wb_sales".to_string(), 3);
+        settings.sla_hours.insert("wb_orders".to_string(), 0);
+        assert_eq!(settings.sla_hours_for(FreshnessDataset::WbSales), 3);
+        assert_eq!(settings.sla_hours_for(FreshnessDataset::WbOrders), 6);
+        assert_eq!(settings.sla_hours_for(FreshnessDataset::Ozon), 24);
+    }
+}
@@ -0,0 +1,170 @@
+//! Шаблоны описаний документов: поле `description` агрегата при импорте
+//! собирается по шаблону с плейсхолдерами вида `{supplier_article}`.
+
+use serde::{Deserialize, Serialize};
+
+/// Максимальная длина шаблона.
+pub const MAX_TEMPLATE_LEN: usize = 500;
+
+/// Агрегат, для которого настраивается шаблон.
+pub struct TemplateEntity {
+    pub entity_type: &'static str,
+    pub label: &'static str,
+    /// Описание, которое импорт формирует без шаблона.
+    pub default_template: &'static str,
+    /// `(имя, подпись, пример значения)`
+    pub placeholders: &'static [(&'static str, &'static str, &'static str)],
+}
+
+pub const TEMPLATE_ENTITIES: &[TemplateEntity] = &[
+    TemplateEntity {
+        entity_type: "a012_wb_sales",
+        label: "WB Продажи",
+        default_template: "WB {event_type} {supplier_article}",
+        placeholders: &[
+            ("document_no", "Номер (srid)", "1234567890123456789.0.0"),
+            ("sale_id", "ID продажи", "S9876543210"),
+            ("event_type", "Событие (sale/return)", "sale"),
+            ("supplier_article", "Артикул продавца", "ART-001"),
+            ("nm_id", "nmId", "123456789"),
+            ("product_name", "Наименование", "Футболка хлопковая"),
+            ("sale_dt", "Дата продажи", "05.11.2025"),
+            ("qty", "Количество", "1"),
+            ("finished_price", "Цена покупателя", "1490.00"),
+            ("warehouse_name", "Склад", "Коледино"),
+        ],
+    },
+    TemplateEntity {
+        entity_type: "a015_wb_orders",
+        label: "WB Заказы",
+        default_template: "WB Order {supplier_article} - {order_dt}",
+        placeholders: &[
+            ("document_no", "Номер (srid)", "1234567890123456789.0.0"),
+            ("supplier_article", "Артикул продавца", "ART-001"),
+            ("nm_id", "nmId", "123456789"),
+            ("brand", "Бренд", "Brand"),
+            ("subject", "Предмет", "Футболки"),
+            ("order_dt", "Дата и время заказа", "2025-11-05 16:52:58"),
+            ("order_date", "Дата заказа", "05.11.2025"),
+            ("finished_price", "Цена покупателя", "1490.00"),
+            ("warehouse_name", "Склад", "Коледино"),
+        ],
+    },
+];
+
+pub fn template_entity(entity_type: &str) -> Option<&'static TemplateEntity> {
+    TEMPLATE_ENTITIES
+        .iter()
+        .find(|e| e.entity_type == entity_type)
+}
+
+/// Имена `{...}` в шаблоне по порядку появления.
+fn placeholder_names(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => {
+                names.push(&after[..end]);
+                rest = &after[end + 1..];
+            }
+            None => break,
+        }
+    }
+    names
+}
+
+/// Плейсхолдеры шаблона, которых нет у агрегата.
+pub fn unknown_placeholders(entity: &TemplateEntity, template: &str) -> Vec<String> {
+    placeholder_names(template)
+        .into_iter()
+        .filter(|name| !entity.placeholders.iter().any(|(p, _, _)| p == name))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Подставляет значения в шаблон; отсутствующее значение — пустая строка,
+/// повторные пробелы схлопываются.
+pub fn render_template(template: &str, values: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                if let Some((_, value)) = values.iter().find(|(n, _)| *n == name) {
+                    out.push_str(value);
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Значения-примеры для предпросмотра шаблона.
+pub fn sample_values(entity: &TemplateEntity) -> Vec<(&'static str, String)> {
+    entity
+        .placeholders
+        .iter()
+        .map(|(name, _, sample)| (*name, sample.to_string()))
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DescriptionTemplateDto {
+    pub entity_type: String,
+    /// `None` — шаблон не задан, импорт пишет описание по умолчанию.
+    pub template: Option<String>,
+    pub updated_at: Option<String>,
+    pub updated_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DescriptionTemplateUpsertRequest {
+    pub template: String,
+}
+
+/// Итог повторного применения шаблона к уже загруженным документам.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApplyDescriptionTemplateResponse {
+    pub scanned: u64,
+    pub updated: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_known_and_missing_placeholders() {
+        let values = vec![
+            ("supplier_article", "ART-1".to_string()),
+            ("event_type", "sale".to_string()),
+            ("sale_dt", String::new()),
+        ];
+        assert_eq!(
+            render_template("WB {event_type} {supplier_article} от {sale_dt}", &values),
+            "WB sale ART-1 от"
+        );
+        assert_eq!(render_template("{nope} x {", &values), "x {");
+    }
+
+    #[test]
+    fn reports_unknown_placeholders() {
+        let entity = template_entity("a012_wb_sales").unwrap();
+        assert!(unknown_placeholders(entity, entity.default_template).is_empty());
+        assert_eq!(
+            unknown_placeholders(entity, "{supplier_article} {order_dt}"),
+            vec!["order_dt".to_string()]
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//! Журнал действий с документами: кто и когда создал, изменил, провёл,
+//! распровёл или удалил документ. Показывается на вкладке «История» карточки.
+
+use serde::{Deserialize, Serialize};
+
+/// Действие над документом.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentAuditAction {
+    Created,
+    Updated,
+    Posted,
+    Unposted,
+    Deleted,
+}
+
+impl DocumentAuditAction {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Posted => "posted",
+            Self::Unposted => "unposted",
+            Self::Deleted => "deleted",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        [
+            Self::Created,
+            Self::Updated,
+            Self::Posted,
+            Self::Unposted,
+            Self::Deleted,
+        ]
+        .into_iter()
+        .find(|action| action.code() == code)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Created => "Создан",
+            Self::Updated => "Изменён",
+            Self::Posted => "Проведён",
+            Self::Unposted => "Проведение отменено",
+            Self::Deleted => "Удалён",
+        }
+    }
+}
+
+/// Одна запись журнала.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DocumentAuditEntryDto {
+    pub id: i64,
+    /// RFC3339
+    pub created_at: String,
+    pub user_id: Option<String>,
+    /// Логин на момент просмотра; `None`, если пользователь удалён
+    pub username: Option<String>,
+    pub action: DocumentAuditAction,
+    /// Изменённые поля «поле: было → стало»; `None`, если изменений полей нет
+    pub summary: Option<String>,
+}
@@ -0,0 +1,94 @@
+//! Режим окружения (dev/staging/prod) из `config.toml`: показывается баннером в
+//! шапке и ограничивает опасные операции администратора (очистки, массовые удаления).
+
+use serde::{Deserialize, Serialize};
+
+/// Заголовок с токеном подтверждения опасной операции.
+pub const CONFIRM_TOKEN_HEADER: &str = "X-Confirm-Token";
+
+/// Срок действия токена подтверждения.
+pub const CONFIRM_TOKEN_TTL_SECS: i64 = 300;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvironmentMode {
+    #[default]
+    Dev,
+    Staging,
+    Prod,
+}
+
+impl EnvironmentMode {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Dev => "dev",
+            Self::Staging => "staging",
+            Self::Prod => "prod",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Dev => "Разработка",
+            Self::Staging => "Тестовый стенд",
+            Self::Prod => "Рабочая база",
+        }
+    }
+
+    /// Опасные операции требуют токена подтверждения.
+    pub fn requires_confirmation(&self) -> bool {
+        !matches!(self, Self::Dev)
+    }
+
+    /// Для получения токена нужно ввести код режима вручную.
+    pub fn requires_typed_confirmation(&self) -> bool {
+        matches!(self, Self::Prod)
+    }
+}
+
+/// Ключ опасной операции: метод и фактический путь запроса.
+pub fn danger_action(method: &str, path: &str) -> String {
+    format!("{} {}", method.to_ascii_uppercase(), path)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EnvironmentInfoDto {
+    pub mode: EnvironmentMode,
+    /// Подпись стенда из конфига, например «Стенд Иванова».
+    #[serde(default)]
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DangerConfirmRequest {
+    /// См. [`danger_action`].
+    pub action: String,
+    /// Введённый пользователем код режима; обязателен для prod.
+    #[serde(default)]
+    pub typed_mode: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DangerConfirmResponse {
+    /// `None` — в текущем режиме подтверждение не требуется.
+    pub token: Option<String>,
+    pub expires_at: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mode_parses_from_lowercase_and_guards() {
+        let mode: EnvironmentMode = serde_json::from_str("\"prod\"").unwrap();
+        assert_eq!(mode, EnvironmentMode::Prod);
+        assert!(mode.requires_typed_confirmation());
+        assert!(EnvironmentMode::Staging.requires_confirmation());
+        assert!(!EnvironmentMode::Dev.requires_confirmation());
+        assert_eq!(
+            danger_action("post", "/api/sys/raw-storage/cleanup"),
+            "POST /api/sys/raw-storage/cleanup"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Профили выгрузок: сохранённый отбор документов + набор колонок + формат файла.
+//!
+//! Регулярные выгрузки для партнёров (например, все продажи с меткой «партнёр-X»
+//! за последнюю неделю) раньше собирались вручную. Профиль запускается по кнопке
+//! или по расписанию (cron); результат — обычная фоновая выгрузка в «Мои выгрузки»
+//! владельца профиля.
+
+use serde::{Deserialize, Serialize};
+
+use super::exports::ExportFileFormat;
+
+/// Колонка, которую можно включить в профиль.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportColumnInfo {
+    pub key: &'static str,
+    pub label: &'static str,
+}
+
+/// Тип документов, доступный для профилей: те же агрегаты, что помечаются
+/// метками на странице «История операций».
+#[derive(Debug, Clone, Copy)]
+pub struct ExportEntityInfo {
+    pub entity_type: &'static str,
+    pub label: &'static str,
+    /// Колонки в порядке по умолчанию
+    pub columns: &'static [ExportColumnInfo],
+}
+
+const fn col(key: &'static str, label: &'static str) -> ExportColumnInfo {
+    ExportColumnInfo { key, label }
+}
+
+pub const EXPORT_PROFILE_ENTITIES: &[ExportEntityInfo] = &[
+    ExportEntityInfo {
+        entity_type: "a012_wb_sales",
+        label: "Продажи WB",
+        columns: &[
+            col("document_no", "SRID"),
+            col("document_date", "Дата продажи"),
+            col("event_type", "Тип события"),
+            col("supplier_article", "Артикул продавца"),
+            col("nm_id", "nmID"),
+            col("barcode", "Баркод"),
+            col("product_name", "Товар"),
+            col("qty", "Кол-во"),
+            col("total_price", "Цена до скидок"),
+            col("finished_price", "Цена покупателя"),
+            col("is_posted", "Проведён"),
+            col("tags", "Метки"),
+            col("created_at", "Загружен"),
+        ],
+    },
+    ExportEntityInfo {
+        entity_type: "a015_wb_orders",
+        label: "Заказы WB",
+        columns: &[
+            col("document_no", "SRID"),
+            col("document_date", "Дата заказа"),
+            col("g_number", "Номер корзины"),
+            col("spp", "СПП, %"),
+            col("is_cancel", "Отменён"),
+            col("cancel_date", "Дата отмены"),
+            col("is_posted", "Проведён"),
+            col("tags", "Метки"),
+            col("created_at", "Загружен"),
+        ],
+    },
+    ExportEntityInfo {
+        entity_type: "a010_ozon_fbs_posting",
+        label: "Отправления Ozon FBS",
+        columns: &[
+            col("document_no", "Номер отправления"),
+            col("document_date", "Дата доставки"),
+            col("status_norm", "Статус"),
+            col("substatus_raw", "Подстатус"),
+            col("is_posted", "Проведён"),
+            col("tags", "Метки"),
+            col("created_at", "Загружен"),
+        ],
+    },
+    ExportEntityInfo {
+        entity_type: "a011_ozon_fbo_posting",
+        label: "Отправления Ozon FBO",
+        columns: &[
+            col("document_no", "Номер отправления"),
+            col("document_date", "Дата создания"),
+            col("status_norm", "Статус"),
+            col("substatus_raw", "Подстатус"),
+            col("is_posted", "Проведён"),
+            col("tags", "Метки"),
+            col("created_at", "Загружен"),
+        ],
+    },
+];
+
+pub fn entity_info(entity_type: &str) -> Option<&'static ExportEntityInfo> {
+    EXPORT_PROFILE_ENTITIES
+        .iter()
+        .find(|e| e.entity_type == entity_type)
+}
+
+/// Длина периода «последние N дней», максимум.
+pub const EXPORT_PROFILE_MAX_PERIOD_DAYS: i64 = 366;
+
+/// Сохранённый отбор документов профиля.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportProfileFilter {
+    /// Документы хотя бы с одной из меток; пусто — без отбора по меткам
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Кабинет маркетплейса (a006)
+    #[serde(default)]
+    pub connection_id: Option<String>,
+    /// Последние N дней по дате документа, включая сегодня; пусто — за всё время
+    #[serde(default)]
+    pub period_days: Option<i64>,
+    /// Только проведённые документы
+    #[serde(default)]
+    pub posted_only: bool,
+}
+
+/// Создание / изменение профиля (POST / PUT `/api/system/export-profiles`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportProfileSaveDto {
+    pub name: String,
+    pub entity_type: String,
+    #[serde(default)]
+    pub filter: ExportProfileFilter,
+    /// Ключи колонок из каталога, в порядке вывода
+    pub columns: Vec<String>,
+    #[serde(default)]
+    pub format: ExportFileFormat,
+    /// Расписание в формате регламентных заданий: `сек мин час день месяц день_недели`
+    #[serde(default)]
+    pub schedule_cron: Option<String>,
+    #[serde(default)]
+    pub schedule_enabled: bool,
+}
+
+impl ExportProfileSaveDto {
+    /// Проверка без обращения к БД; cron разбирается на сервере.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Укажите название профиля".to_string());
+        }
+        let Some(entity) = entity_info(&self.entity_type) else {
+            return Err(format!("Неизвестный тип документов: {}", self.entity_type));
+        };
+        if self.columns.is_empty() {
+            return Err("Выберите хотя бы одну колонку".to_string());
+        }
+        if let Some(unknown) = self
+            .columns
+            .iter()
+            .find(|key| !entity.columns.iter().any(|c| c.key == key.as_str()))
+        {
+            return Err(format!("Неизвестная колонка: {}", unknown));
+        }
+        if self.filter.tags.iter().any(|t| t.trim().is_empty()) {
+            return Err("Пустая метка в отборе".to_string());
+        }
+        if let Some(days) = self.filter.period_days {
+            if !(1..=EXPORT_PROFILE_MAX_PERIOD_DAYS).contains(&days) {
+                return Err(format!(
+                    "Период — от 1 до {} дней",
+                    EXPORT_PROFILE_MAX_PERIOD_DAYS
+                ));
+            }
+        }
+        let has_cron = self
+            .schedule_cron
+            .as_deref()
+            .is_some_and(|c| !c.trim().is_empty());
+        if self.schedule_enabled && !has_cron {
+            return Err("Для запуска по расписанию укажите cron".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportProfileDto {
+    pub id: String,
+    pub name: String,
+    pub entity_type: String,
+    pub filter: ExportProfileFilter,
+    pub columns: Vec<String>,
+    pub format: ExportFileFormat,
+    pub schedule_cron: Option<String>,
+    pub schedule_enabled: bool,
+    /// Следующий запуск по расписанию (UTC ISO8601)
+    pub next_run_at: Option<String>,
+    pub last_run_at: Option<String>,
+    /// Выгрузка последнего запуска («Мои выгрузки»)
+    pub last_job_id: Option<String>,
+    /// Почему не удалось поставить последний запуск
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportProfileListResponse {
+    pub items: Vec<ExportProfileDto>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dto() -> ExportProfileSaveDto {
+        ExportProfileSaveDto {
+            name: "Партнёр X — продажи".to_string(),
+            entity_type: "a012_wb_sales".to_string(),
+            filter: ExportProfileFilter {
+                tags: vec!["партнёр-x".to_string()],
+                period_days: Some(7),
+                ..Default::default()
+            },
+            columns: vec!["document_no".to_string(), "finished_price".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn validate_accepts_catalog_columns_only() {
+        assert!(dto().validate().is_ok());
+
+        let mut wrong_column = dto();
+        wrong_column.columns.push("g_number".to_string());
+        assert!(wrong_column.validate().is_err());
+
+        let mut unknown_entity = dto();
+        unknown_entity.entity_type = "a999".to_string();
+        assert!(unknown_entity.validate().is_err());
+    }
+
+    #[test]
+    fn validate_checks_period_and_schedule() {
+        let mut long_period = dto();
+        long_period.filter.period_days = Some(EXPORT_PROFILE_MAX_PERIOD_DAYS + 1);
+        assert!(long_period.validate().is_err());
+
+        let mut no_cron = dto();
+        no_cron.schedule_enabled = true;
+        assert!(no_cron.validate().is_err());
+        no_cron.schedule_cron = Some("0 0 7 * * Mon".to_string());
+        assert!(no_cron.validate().is_ok());
+    }
+
+    #[test]
+    fn catalog_column_keys_are_unique_per_entity() {
+        for entity in EXPORT_PROFILE_ENTITIES {
+            let mut keys: Vec<_> = entity.columns.iter().map(|c| c.key).collect();
+            keys.sort_unstable();
+            keys.dedup();
+            assert_eq!(keys.len(), entity.columns.len(), "{}", entity.entity_type);
+        }
+    }
+}
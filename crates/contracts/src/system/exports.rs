@@ -0,0 +1,134 @@
+//! Фоновые выгрузки («Мои выгрузки»): тяжёлый экспорт выполняется задачей на сервере,
+//! результат сохраняется файлом в S3 (категория `exports`) и доступен для скачивания
+//! до истечения срока хранения.
+
+use serde::{Deserialize, Serialize};
+
+/// Сколько дней хранится готовый файл выгрузки.
+pub const EXPORT_TTL_DAYS: i64 = 7;
+
+/// Вид выгрузки: p903 — финансовый отчёт WB.
+pub const EXPORT_KIND_P903_FINANCE_REPORT: &str = "p903_wb_finance_report";
+
+/// Вид выгрузки: запуск профиля выгрузки (см. `export_profiles`).
+pub const EXPORT_KIND_PROFILE: &str = "export_profile";
+
+/// Формат файла выгрузки.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFileFormat {
+    /// `;`, UTF-8 BOM, десятичная запятая — открывается в Excel как есть
+    #[default]
+    Csv,
+    Xlsx,
+    /// Объект JSON на строку — для загрузки в системы партнёров
+    Ndjson,
+}
+
+impl ExportFileFormat {
+    pub const ALL: [ExportFileFormat; 3] = [Self::Csv, Self::Xlsx, Self::Ndjson];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+            Self::Ndjson => "ndjson",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Csv => "CSV (Excel)",
+            Self::Xlsx => "XLSX",
+            Self::Ndjson => "NDJSON",
+        }
+    }
+
+    /// Расширение файла без точки.
+    pub fn extension(self) -> &'static str {
+        self.code()
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            Self::Ndjson => "application/x-ndjson",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportJobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+    /// Файл удалён по сроку хранения
+    Expired,
+}
+
+impl ExportJobStatus {
+    pub const ALL: [ExportJobStatus; 5] = [
+        ExportJobStatus::Queued,
+        ExportJobStatus::Running,
+        ExportJobStatus::Done,
+        ExportJobStatus::Failed,
+        ExportJobStatus::Expired,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Done => "done",
+            Self::Failed => "failed",
+            Self::Expired => "expired",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Queued => "В очереди",
+            Self::Running => "Формируется",
+            Self::Done => "Готово",
+            Self::Failed => "Ошибка",
+            Self::Expired => "Срок истёк",
+        }
+    }
+
+    pub fn is_finished(self) -> bool {
+        !matches!(self, Self::Queued | Self::Running)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportJobDto {
+    pub id: String,
+    pub kind: String,
+    /// Человекочитаемое описание: что и за какой период выгружено
+    pub title: String,
+    pub status: ExportJobStatus,
+    pub filename: Option<String>,
+    pub size_bytes: Option<i64>,
+    pub row_count: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub finished_at: Option<String>,
+    /// До какого момента файл доступен для скачивания
+    pub expires_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportJobListResponse {
+    pub items: Vec<ExportJobDto>,
+}
@@ -0,0 +1,154 @@
+//! Внешние ссылки документа: номер претензии WB, номер документа в ERP и т.п.
+//!
+//! Ссылка — пара «система + ключ» (и необязательный URL), привязанная к документу
+//! агрегата. По ключу документ находится глобальным поиском, поэтому сквозная
+//! трассировка между системами не живёт в комментариях.
+
+use serde::{Deserialize, Serialize};
+
+pub const MAX_SYSTEM_LEN: usize = 50;
+pub const MAX_KEY_LEN: usize = 200;
+pub const MAX_URL_LEN: usize = 1000;
+
+/// Документ, к которому можно привязать ссылку.
+pub struct ExternalRefDocument {
+    pub entity_type: &'static str,
+    pub label: &'static str,
+    /// Префикс ключа вкладки карточки; ключ — префикс + id документа.
+    pub tab_prefix: &'static str,
+}
+
+pub const EXTERNAL_REF_DOCUMENTS: &[ExternalRefDocument] = &[
+    ExternalRefDocument {
+        entity_type: "a012_wb_sales",
+        label: "WB Продажи",
+        tab_prefix: "a012_wb_sales_details_",
+    },
+    ExternalRefDocument {
+        entity_type: "a015_wb_orders",
+        label: "WB Заказы",
+        tab_prefix: "a015_wb_orders_details_",
+    },
+    ExternalRefDocument {
+        entity_type: "a032_wb_returns_claims",
+        label: "WB Заявки на возврат",
+        tab_prefix: "a032_wb_returns_claims_details_",
+    },
+];
+
+pub fn external_ref_document(entity_type: &str) -> Option<&'static ExternalRefDocument> {
+    EXTERNAL_REF_DOCUMENTS
+        .iter()
+        .find(|d| d.entity_type == entity_type)
+}
+
+/// Подсказки для поля «Система»; допускается и произвольное значение.
+pub const EXTERNAL_REF_SYSTEM_SUGGESTIONS: &[&str] = &[
+    "Претензия WB",
+    "1С",
+    "ERP",
+    "Ozon",
+    "Яндекс Маркет",
+    "Почта",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExternalRefDto {
+    pub id: String,
+    pub entity_type: String,
+    pub document_id: String,
+    /// Внешняя система, например «Претензия WB» или «1С»
+    pub system: String,
+    /// Номер/идентификатор в этой системе
+    pub key: String,
+    pub url: Option<String>,
+    /// Логин добавившего
+    pub created_by: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Запрос `POST /api/external-refs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateExternalRefRequest {
+    pub entity_type: String,
+    pub document_id: String,
+    #[serde(flatten)]
+    pub fields: ExternalRefFields,
+}
+
+/// Редактируемые поля ссылки; тело `PUT /api/external-refs/:id`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExternalRefFields {
+    pub system: String,
+    pub key: String,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+impl ExternalRefFields {
+    /// Обрезает пробелы, пустой URL превращает в `None` и проверяет значения.
+    /// Текст ошибки — для пользователя.
+    pub fn normalized(&self) -> Result<ExternalRefFields, String> {
+        let system = self.system.trim().to_string();
+        let key = self.key.trim().to_string();
+        let url = self
+            .url
+            .as_deref()
+            .map(str::trim)
+            .filter(|u| !u.is_empty())
+            .map(str::to_string);
+
+        if system.is_empty() || system.chars().count() > MAX_SYSTEM_LEN {
+            return Err(format!(
+                "Система обязательна, до {} символов",
+                MAX_SYSTEM_LEN
+            ));
+        }
+        if key.is_empty() || key.chars().count() > MAX_KEY_LEN {
+            return Err(format!("Ключ обязателен, до {} символов", MAX_KEY_LEN));
+        }
+        if let Some(url) = &url {
+            if url.chars().count() > MAX_URL_LEN
+                || !(url.starts_with("http://") || url.starts_with("https://"))
+            {
+                return Err("URL должен начинаться с http:// или https://".to_string());
+            }
+        }
+        Ok(ExternalRefFields { system, key, url })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalized_trims_and_validates() {
+        let fields = ExternalRefFields {
+            system: "  Претензия WB ".to_string(),
+            key: " 12345 ".to_string(),
+            url: Some("  ".to_string()),
+        };
+        assert_eq!(
+            fields.normalized().unwrap(),
+            ExternalRefFields {
+                system: "Претензия WB".to_string(),
+                key: "12345".to_string(),
+                url: None,
+            }
+        );
+
+        let no_key = ExternalRefFields {
+            key: " ".to_string(),
+            ..fields.clone()
+        };
+        assert!(no_key.normalized().is_err());
+
+        let bad_url = ExternalRefFields {
+            url: Some("javascript:alert(1)".to_string()),
+            ..fields
+        };
+        assert!(bad_url.normalized().is_err());
+    }
+}
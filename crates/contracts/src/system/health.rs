@@ -0,0 +1,37 @@
+//! Проверки живости и готовности сервера (`/healthz`, `/readyz`) для reverse proxy
+//! и мониторинга.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Ok,
+    /// Проверка не выполняется (например, воркер выключен в config.toml)
+    Disabled,
+    /// Общий статус: не прошла некритичная проверка, сервер готов
+    Degraded,
+    Fail,
+}
+
+/// Результат одной проверки зависимости.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheckDto {
+    pub name: String,
+    pub status: HealthStatus,
+    /// Провал критичной проверки делает сервер не готовым (503 на `/readyz`)
+    pub critical: bool,
+    pub latency_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Ответ `/healthz` и `/readyz`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReportDto {
+    pub status: HealthStatus,
+    pub ready: bool,
+    pub uptime_secs: u64,
+    pub checked_at: String,
+    pub checks: Vec<HealthCheckDto>,
+}
@@ -0,0 +1,73 @@
+//! Происхождение импортированных данных (`sys_import_batches`).
+//!
+//! На каждый пакет, полученный от API маркетплейса, сохраняются параметры запроса,
+//! sha256 тела ответа и число строк. Документ ссылается на сессию импорта
+//! (`source_meta.import_session_id`), сессия — на свои пакеты.
+
+use serde::{Deserialize, Serialize};
+
+/// Один пакет (страница ответа API).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportBatchDto {
+    pub id: String,
+    pub session_id: String,
+    pub connection_id: String,
+    pub aggregate_index: String,
+    pub endpoint: String,
+    /// JSON параметров запроса; ключи доступа не сохраняются.
+    pub request_params: String,
+    pub response_sha256: String,
+    pub response_bytes: i64,
+    pub row_count: i64,
+    pub fetched_at: String,
+}
+
+/// Раздел «Происхождение импорта» для запуска или документа.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportProvenanceResponse {
+    pub session_id: String,
+    /// В порядке получения.
+    pub batches: Vec<ImportBatchDto>,
+    pub total_rows: i64,
+    pub total_bytes: i64,
+}
+
+impl ImportProvenanceResponse {
+    pub fn new(session_id: String, batches: Vec<ImportBatchDto>) -> Self {
+        Self {
+            session_id,
+            total_rows: batches.iter().map(|b| b.row_count).sum(),
+            total_bytes: batches.iter().map(|b| b.response_bytes).sum(),
+            batches,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(rows: i64, bytes: i64) -> ImportBatchDto {
+        ImportBatchDto {
+            id: String::new(),
+            session_id: "s1".to_string(),
+            connection_id: "c1".to_string(),
+            aggregate_index: "a012_wb_sales".to_string(),
+            endpoint: "https://statistics-api.wildberries.ru/api/v1/supplier/sales".to_string(),
+            request_params: "{}".to_string(),
+            response_sha256: String::new(),
+            response_bytes: bytes,
+            row_count: rows,
+            fetched_at: "2026-03-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn totals_sum_batches() {
+        let response =
+            ImportProvenanceResponse::new("s1".to_string(), vec![batch(10, 500), batch(3, 120)]);
+        assert_eq!(response.total_rows, 13);
+        assert_eq!(response.total_bytes, 620);
+        assert_eq!(response.batches.len(), 2);
+    }
+}
@@ -0,0 +1,48 @@
+//! Просмотр журнала сервера (`logs/backend.log`) из админки — без доступа по SSH.
+
+use serde::{Deserialize, Serialize};
+
+/// Уровни в порядке убывания важности (как в `tracing`).
+pub const LOG_LEVELS: [&str; 5] = ["ERROR", "WARN", "INFO", "DEBUG", "TRACE"];
+
+/// Фильтры выборки; пустые поля не фильтруют.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogQuery {
+    /// Минимальный уровень: `ERROR` | `WARN` | `INFO` | `DEBUG` | `TRACE`
+    #[serde(default)]
+    pub level: Option<String>,
+    /// Подстрока модуля (target), например `system::tasks`
+    #[serde(default)]
+    pub module: Option<String>,
+    /// Поиск по тексту сообщения без учёта регистра
+    #[serde(default)]
+    pub q: Option<String>,
+    /// Correlation id запроса (`x-request-id`)
+    #[serde(default)]
+    pub request_id: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LogEntry {
+    /// Время из строки журнала (UTC RFC3339), если строка его содержит
+    pub timestamp: Option<String>,
+    pub level: String,
+    /// Модуль-источник (`backend::system::tasks::worker`)
+    pub target: String,
+    pub request_id: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogQueryResponse {
+    /// Новые записи первыми
+    pub entries: Vec<LogEntry>,
+    /// Сколько записей просмотрено в хвосте файла
+    pub scanned: usize,
+    /// Совпадений больше, чем `limit`
+    pub truncated: bool,
+    /// Размер файла журнала (байт)
+    pub file_size: u64,
+}
@@ -0,0 +1,152 @@
+//! Уведомления пользователей: события, каналы доставки и персональные настройки
+//! (матрица «событие × канал»). Диспетчер на сервере отправляет уведомление только
+//! по включённым у пользователя каналам.
+
+use serde::{Deserialize, Serialize};
+
+/// Событие, о котором уведомляется пользователь.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// Плановый импорт завершился ошибкой
+    ImportFailed,
+    /// Пользователю назначен документ
+    DocumentAssigned,
+    /// Сработал алерт (складские алерты и т.п.)
+    AlertFired,
+    /// Фоновая выгрузка готова (или завершилась ошибкой)
+    ExportReady,
+    /// Скоро истекает срок действия учётной записи
+    AccessExpiring,
+}
+
+impl NotificationEvent {
+    pub const ALL: [NotificationEvent; 5] = [
+        NotificationEvent::ImportFailed,
+        NotificationEvent::DocumentAssigned,
+        NotificationEvent::AlertFired,
+        NotificationEvent::ExportReady,
+        NotificationEvent::AccessExpiring,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::ImportFailed => "import_failed",
+            Self::DocumentAssigned => "document_assigned",
+            Self::AlertFired => "alert_fired",
+            Self::ExportReady => "export_ready",
+            Self::AccessExpiring => "access_expiring",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ImportFailed => "Ошибка импорта",
+            Self::DocumentAssigned => "Назначен документ",
+            Self::AlertFired => "Сработал алерт",
+            Self::ExportReady => "Выгрузка готова",
+            Self::AccessExpiring => "Истекает срок доступа",
+        }
+    }
+}
+
+/// Канал доставки уведомления.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationChannel {
+    /// Колокольчик в шапке приложения
+    InApp,
+    Email,
+    Telegram,
+}
+
+impl NotificationChannel {
+    pub const ALL: [NotificationChannel; 3] = [
+        NotificationChannel::InApp,
+        NotificationChannel::Email,
+        NotificationChannel::Telegram,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::InApp => "in_app",
+            Self::Email => "email",
+            Self::Telegram => "telegram",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::InApp => "В приложении",
+            Self::Email => "Email",
+            Self::Telegram => "Telegram",
+        }
+    }
+
+    /// Значение по умолчанию для ещё не настроенной ячейки: только в приложении.
+    pub fn enabled_by_default(self) -> bool {
+        self == Self::InApp
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NotificationPreference {
+    pub event: NotificationEvent,
+    pub channel: NotificationChannel,
+    pub enabled: bool,
+}
+
+/// Настройки уведомлений текущего пользователя.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NotificationPreferencesDto {
+    /// Полная матрица: по ячейке на каждую пару событие × канал
+    pub preferences: Vec<NotificationPreference>,
+    /// Адрес из профиля пользователя (только чтение)
+    pub email: Option<String>,
+    pub telegram_chat_id: Option<String>,
+    /// Канал настроен на сервере (config.toml)
+    pub email_available: bool,
+    pub telegram_available: bool,
+}
+
+impl NotificationPreferencesDto {
+    pub fn is_enabled(&self, event: NotificationEvent, channel: NotificationChannel) -> bool {
+        self.preferences
+            .iter()
+            .find(|p| p.event == event && p.channel == channel)
+            .map_or_else(|| channel.enabled_by_default(), |p| p.enabled)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NotificationPreferencesUpdate {
+    pub preferences: Vec<NotificationPreference>,
+    pub telegram_chat_id: Option<String>,
+}
+
+/// Уведомление во входящих (канал «в приложении»).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NotificationDto {
+    pub id: String,
+    pub event: NotificationEvent,
+    pub title: String,
+    pub body: String,
+    /// Ключ вкладки, которую открыть по клику
+    pub tab_key: Option<String>,
+    pub created_at: String,
+    pub read_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NotificationInboxDto {
+    pub items: Vec<NotificationDto>,
+    pub unread: i64,
+}
@@ -0,0 +1,161 @@
+//! Пакетные операции: общий API для массовых действий над документами.
+//!
+//! `POST /api/operations` принимает [`OperationRequest`] и сразу возвращает
+//! `operation_id`; операция выполняется на сервере в фоне. Статус, прогресс и итог —
+//! `GET /api/operations/{id}`, последние операции пользователя — `GET /api/operations`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::system::bulk_ops::{BulkActionResultDto, BulkDocumentRef};
+
+/// Сколько элементов принимается в одной операции.
+pub const OPERATION_MAX_ITEMS: usize = 10_000;
+
+/// Запрос пакетной операции: вид (`kind`) и его параметры (`params`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", content = "params", rename_all = "snake_case")]
+pub enum OperationRequest {
+    /// Проведение документов одного агрегата (`a012_wb_sales`, `a013_ym_order`, `a016_ym_returns`).
+    PostDocuments {
+        entity_type: String,
+        ids: Vec<String>,
+    },
+    /// Отмена проведения; снимок проведённых документов попадает в «Историю операций» для отмены.
+    UnpostDocuments {
+        entity_type: String,
+        ids: Vec<String>,
+    },
+    /// Проведение документов, найденных по вставленному списку номеров.
+    PostByList { documents: Vec<BulkDocumentRef> },
+    /// Метка на документы, найденные по вставленному списку номеров.
+    TagByList {
+        documents: Vec<BulkDocumentRef>,
+        tag: String,
+    },
+}
+
+impl OperationRequest {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::PostDocuments { .. } => "post_documents",
+            Self::UnpostDocuments { .. } => "unpost_documents",
+            Self::PostByList { .. } => "post_by_list",
+            Self::TagByList { .. } => "tag_by_list",
+        }
+    }
+
+    /// Сколько элементов обработает операция (знаменатель прогресса).
+    pub fn item_count(&self) -> usize {
+        match self {
+            Self::PostDocuments { ids, .. } | Self::UnpostDocuments { ids, .. } => ids.len(),
+            Self::PostByList { documents } | Self::TagByList { documents, .. } => documents.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationStatus {
+    Queued,
+    Running,
+    /// Выполнена; ошибки по отдельным элементам — в `result.errors`.
+    Completed,
+    /// Не выполнилась целиком (ошибка операции или перезапуск сервера).
+    Failed,
+}
+
+impl OperationStatus {
+    pub const ALL: [OperationStatus; 4] = [
+        OperationStatus::Queued,
+        OperationStatus::Running,
+        OperationStatus::Completed,
+        OperationStatus::Failed,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Queued => "В очереди",
+            Self::Running => "Выполняется",
+            Self::Completed => "Готово",
+            Self::Failed => "Ошибка",
+        }
+    }
+
+    pub fn is_finished(self) -> bool {
+        !matches!(self, Self::Queued | Self::Running)
+    }
+}
+
+/// Ответ `POST /api/operations`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartOperationResponse {
+    pub operation_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OperationDto {
+    pub id: String,
+    pub kind: String,
+    /// Человекочитаемое описание: что и над сколькими документами
+    pub title: String,
+    pub status: OperationStatus,
+    /// Обработано элементов из `total`
+    pub processed: usize,
+    pub total: usize,
+    /// Итог по элементам; есть у завершённой операции
+    pub result: Option<BulkActionResultDto>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+impl OperationDto {
+    /// Доля выполнения 0..=100.
+    pub fn percent(&self) -> u32 {
+        if self.total == 0 {
+            return if self.status.is_finished() { 100 } else { 0 };
+        }
+        (self.processed.min(self.total) * 100 / self.total) as u32
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OperationListResponse {
+    pub items: Vec<OperationDto>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_serializes_as_kind_and_params() {
+        let request = OperationRequest::PostDocuments {
+            entity_type: "a012_wb_sales".to_string(),
+            ids: vec!["1".to_string(), "2".to_string()],
+        };
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["kind"], "post_documents");
+        assert_eq!(json["params"]["entity_type"], "a012_wb_sales");
+        assert_eq!(request.kind(), "post_documents");
+        assert_eq!(request.item_count(), 2);
+        assert_eq!(
+            serde_json::from_value::<OperationRequest>(json).unwrap(),
+            request
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OrganizationOptionDto {
+    pub id: String,
+    pub name: String,
+}
+
+/// Активная организация пользователя: списки и карточки документов
+/// показывают только её данные.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ActiveOrganizationDto {
+    /// `None` — все организации
+    pub organization_id: Option<String>,
+    pub organization_name: Option<String>,
+    /// Организация задана хостом (брендирование) — переключить её нельзя
+    pub locked_by_host: bool,
+    /// Вариант «все организации» доступен только администратору
+    #[serde(default)]
+    pub can_select_all: bool,
+    pub organizations: Vec<OrganizationOptionDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ActiveOrganizationUpdateRequest {
+    /// `None` — все организации (только администратор)
+    pub organization_id: Option<String>,
+}
+
+/// Организации, данные которых доступны пользователю. Для администратора
+/// не ограничивает ничего — ему доступны все организации.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserOrganizationsDto {
+    pub organization_ids: Vec<String>,
+}
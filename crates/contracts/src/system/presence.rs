@@ -0,0 +1,116 @@
+//! Присутствие пользователей на формах документов: кто сейчас открыл запись и кто её
+//! редактирует. Снимки рассылаются сервером по SSE; форма показывает полосу аватаров
+//! и предупреждает перед сохранением поверх чужих изменений.
+
+use serde::{Deserialize, Serialize};
+
+/// Пользователь, открывший документ.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PresenceUserDto {
+    pub user_id: String,
+    pub username: String,
+    /// Есть несохранённые изменения на форме
+    pub editing: bool,
+    /// Когда пользователь открыл документ (RFC3339)
+    pub since: String,
+}
+
+/// Последнее сохранение документа, прошедшее через форму с присутствием.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PresenceSavedDto {
+    pub user_id: String,
+    pub username: String,
+    pub saved_at: String,
+}
+
+/// Состояние присутствия по одному документу (событие `presence` в SSE).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PresenceSnapshot {
+    pub entity: String,
+    pub entity_id: String,
+    pub users: Vec<PresenceUserDto>,
+    pub last_saved: Option<PresenceSavedDto>,
+}
+
+impl PresenceSnapshot {
+    /// Остальные пользователи (кроме `user_id`).
+    pub fn others(&self, user_id: &str) -> Vec<PresenceUserDto> {
+        self.users
+            .iter()
+            .filter(|u| u.user_id != user_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Документ сохранил другой пользователь после момента `since` (RFC3339).
+    pub fn saved_by_other_since(&self, user_id: &str, since: &str) -> Option<&PresenceSavedDto> {
+        self.last_saved
+            .as_ref()
+            .filter(|s| s.user_id != user_id && s.saved_at.as_str() > since)
+    }
+}
+
+/// Запрос на смену состояния текущего пользователя на форме.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PresenceUpdateRequest {
+    pub entity: String,
+    pub entity_id: String,
+    pub editing: bool,
+}
+
+/// Запрос «документ сохранён».
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PresenceSavedRequest {
+    pub entity: String,
+    pub entity_id: String,
+}
+
+/// Инициалы для аватара: первые буквы двух первых слов имени ("ivan.petrov" → "IP").
+pub fn initials(name: &str) -> String {
+    let initials: String = name
+        .split(|c: char| c.is_whitespace() || c == '.' || c == '_' || c == '-' || c == '@')
+        .filter_map(|part| part.chars().next())
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect();
+    if initials.is_empty() {
+        "?".to_string()
+    } else {
+        initials
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initials_from_name_parts() {
+        assert_eq!(initials("ivan.petrov"), "IP");
+        assert_eq!(initials("Анна Смирнова-Козлова"), "АС");
+        assert_eq!(initials("admin"), "A");
+        assert_eq!(initials("  "), "?");
+    }
+
+    #[test]
+    fn saved_by_other_ignores_own_and_older_saves() {
+        let mut snapshot = PresenceSnapshot {
+            last_saved: Some(PresenceSavedDto {
+                user_id: "u2".into(),
+                username: "petrov".into(),
+                saved_at: "2026-01-10T10:05:00+00:00".into(),
+            }),
+            ..Default::default()
+        };
+        assert!(snapshot
+            .saved_by_other_since("u1", "2026-01-10T10:00:00+00:00")
+            .is_some());
+        assert!(snapshot
+            .saved_by_other_since("u1", "2026-01-10T10:06:00+00:00")
+            .is_none());
+        snapshot.last_saved.as_mut().unwrap().user_id = "u1".into();
+        assert!(snapshot
+            .saved_by_other_since("u1", "2026-01-10T10:00:00+00:00")
+            .is_none());
+    }
+}
@@ -0,0 +1,300 @@
+//! Печатные формы документов: HTML-шаблоны претензионных писем и актов с полями
+//! вида `{{supplier_article}}`, которые заполняются данными документа. Готовый
+//! документ открывается в окне печати браузера («Сохранить как PDF») вместо
+//! Word-шаблонов, которые раньше заполняли вручную по вкладке «Связи».
+//!
+//! Поля в двойных фигурных скобках: одинарные встречаются в CSS шаблона.
+
+use serde::{Deserialize, Serialize};
+
+/// Максимальная длина HTML шаблона.
+pub const MAX_PRINT_TEMPLATE_LEN: usize = 100_000;
+
+/// Вид печатной формы.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrintTemplateKind {
+    ClaimLetter,
+    AcceptanceAct,
+    Other,
+}
+
+impl PrintTemplateKind {
+    pub fn all() -> [Self; 3] {
+        [Self::ClaimLetter, Self::AcceptanceAct, Self::Other]
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ClaimLetter => "claim_letter",
+            Self::AcceptanceAct => "acceptance_act",
+            Self::Other => "other",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::all().into_iter().find(|kind| kind.code() == code)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ClaimLetter => "Претензионное письмо",
+            Self::AcceptanceAct => "Акт приёмки",
+            Self::Other => "Другое",
+        }
+    }
+}
+
+/// Агрегат, для документов которого есть печатные формы.
+pub struct PrintTemplateEntity {
+    pub entity_type: &'static str,
+    pub label: &'static str,
+    /// `(имя, подпись, пример значения)`
+    pub placeholders: &'static [(&'static str, &'static str, &'static str)],
+}
+
+/// Поля организации и даты печати — общие для всех агрегатов.
+const COMMON_PLACEHOLDERS: [(&str, &str, &str); 5] = [
+    ("organization_name", "Организация", "ООО «Ромашка»"),
+    (
+        "organization_full_name",
+        "Полное наименование организации",
+        "Общество с ограниченной ответственностью «Ромашка»",
+    ),
+    ("organization_inn", "ИНН организации", "7701234567"),
+    ("organization_kpp", "КПП организации", "770101001"),
+    ("today", "Дата печати", "18.10.2026"),
+];
+
+pub const PRINT_TEMPLATE_ENTITIES: &[PrintTemplateEntity] = &[
+    PrintTemplateEntity {
+        entity_type: "a012_wb_sales",
+        label: "WB Продажи",
+        placeholders: &[
+            COMMON_PLACEHOLDERS[0],
+            COMMON_PLACEHOLDERS[1],
+            COMMON_PLACEHOLDERS[2],
+            COMMON_PLACEHOLDERS[3],
+            COMMON_PLACEHOLDERS[4],
+            ("document_no", "Номер (srid)", "1234567890123456789.0.0"),
+            ("sale_id", "ID продажи", "S9876543210"),
+            ("event_type", "Событие (sale/return)", "return"),
+            ("sale_dt", "Дата продажи", "05.11.2025"),
+            ("supplier_article", "Артикул продавца", "ART-001"),
+            ("nm_id", "nmId", "123456789"),
+            ("barcode", "Штрихкод", "2040000000012"),
+            ("product_name", "Наименование", "Футболка хлопковая"),
+            ("qty", "Количество", "1"),
+            ("finished_price", "Цена покупателя", "1490.00"),
+            ("amount_line", "Сумма", "1490.00"),
+            ("supplier_payout", "К перечислению продавцу", "1180.50"),
+            ("warehouse_name", "Склад", "Коледино"),
+            (
+                "finance_rrd_ids",
+                "Строки финотчёта (rrd_id)",
+                "987654321, 987654322",
+            ),
+        ],
+    },
+    PrintTemplateEntity {
+        entity_type: "a015_wb_orders",
+        label: "WB Заказы",
+        placeholders: &[
+            COMMON_PLACEHOLDERS[0],
+            COMMON_PLACEHOLDERS[1],
+            COMMON_PLACEHOLDERS[2],
+            COMMON_PLACEHOLDERS[3],
+            COMMON_PLACEHOLDERS[4],
+            ("document_no", "Номер (srid)", "1234567890123456789.0.0"),
+            ("order_date", "Дата заказа", "05.11.2025"),
+            ("supplier_article", "Артикул продавца", "ART-001"),
+            ("nm_id", "nmId", "123456789"),
+            ("barcode", "Штрихкод", "2040000000012"),
+            ("product_name", "Наименование", "Футболка хлопковая"),
+            ("brand", "Бренд", "Brand"),
+            ("subject", "Предмет", "Футболки"),
+            ("qty", "Количество", "1"),
+            ("finished_price", "Цена покупателя", "1490.00"),
+            ("warehouse_name", "Склад", "Коледино"),
+            ("region_name", "Регион", "Московская область"),
+            ("income_id", "Номер поставки", "12345678"),
+        ],
+    },
+];
+
+pub fn print_template_entity(entity_type: &str) -> Option<&'static PrintTemplateEntity> {
+    PRINT_TEMPLATE_ENTITIES
+        .iter()
+        .find(|e| e.entity_type == entity_type)
+}
+
+/// Заготовка нового шаблона: каркас страницы A4 с несколькими полями.
+pub fn starter_body_html(kind: PrintTemplateKind) -> String {
+    let (title, body) = match kind {
+        PrintTemplateKind::ClaimLetter => (
+            "Претензия",
+            "<p>От: {{organization_full_name}}, ИНН {{organization_inn}}, КПП {{organization_kpp}}</p>\n\
+             <p>Просим возместить стоимость товара {{product_name}} (артикул {{supplier_article}}, \
+             nmId {{nm_id}}) по документу {{document_no}}.</p>",
+        ),
+        PrintTemplateKind::AcceptanceAct => (
+            "Акт приёмки",
+            "<p>{{organization_full_name}} составил настоящий акт о приёмке товара \
+             {{product_name}} (артикул {{supplier_article}}) по документу {{document_no}}.</p>",
+        ),
+        PrintTemplateKind::Other => ("Документ", "<p>{{document_no}}</p>"),
+    };
+    format!(
+        "<style>\n  body {{ font-family: 'Times New Roman', serif; font-size: 12pt; }}\n  \
+         h1 {{ font-size: 14pt; text-align: center; }}\n</style>\n\
+         <h1>{title}</h1>\n<p style=\"text-align: right;\">{{{{today}}}}</p>\n{body}\n\
+         <p style=\"margin-top: 48pt;\">____________________ / ____________________</p>\n"
+    )
+}
+
+/// Имена `{{...}}` в шаблоне по порядку появления (без пробелов по краям).
+fn placeholder_names(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                names.push(after[..end].trim());
+                rest = &after[end + 2..];
+            }
+            None => break,
+        }
+    }
+    names
+}
+
+/// Поля шаблона, которых нет у агрегата.
+pub fn unknown_placeholders(entity: &PrintTemplateEntity, template: &str) -> Vec<String> {
+    let mut unknown: Vec<String> = Vec::new();
+    for name in placeholder_names(template) {
+        if !entity.placeholders.iter().any(|(p, _, _)| *p == name)
+            && !unknown.iter().any(|u| u == name)
+        {
+            unknown.push(name.to_string());
+        }
+    }
+    unknown
+}
+
+pub fn escape_html(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Подставляет значения в HTML шаблона, экранируя их; отсутствующее значение —
+/// пустая строка. Разметка самого шаблона не меняется.
+pub fn render_print_template(template: &str, values: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let name = after[..end].trim();
+                if let Some((_, value)) = values.iter().find(|(n, _)| *n == name) {
+                    out.push_str(&escape_html(value));
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Значения-примеры для предпросмотра шаблона.
+pub fn sample_values(entity: &PrintTemplateEntity) -> Vec<(&'static str, String)> {
+    entity
+        .placeholders
+        .iter()
+        .map(|(name, _, sample)| (*name, sample.to_string()))
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PrintTemplateDto {
+    pub id: String,
+    pub entity_type: String,
+    pub kind: PrintTemplateKind,
+    pub name: String,
+    pub body_html: String,
+    pub updated_at: String,
+    pub updated_by: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PrintTemplateUpsertRequest {
+    pub entity_type: String,
+    pub kind: PrintTemplateKind,
+    pub name: String,
+    pub body_html: String,
+}
+
+/// Печатная форма, заполненная данными документа.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RenderedPrintDocumentDto {
+    /// Заголовок окна печати и имя PDF по умолчанию
+    pub title: String,
+    pub html: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_and_escapes_values() {
+        let values = vec![
+            ("product_name", "Футболка <XL> & co".to_string()),
+            ("qty", "2".to_string()),
+        ];
+        assert_eq!(
+            render_print_template(
+                "<style>p { margin: 0; }</style><p>{{ product_name }} × {{qty}}{{nope}}</p>",
+                &values
+            ),
+            "<style>p { margin: 0; }</style><p>Футболка &lt;XL&gt; &amp; co × 2</p>"
+        );
+        assert_eq!(render_print_template("a {{qty", &values), "a {{qty");
+    }
+
+    #[test]
+    fn starter_templates_use_known_placeholders() {
+        for entity in PRINT_TEMPLATE_ENTITIES {
+            for kind in PrintTemplateKind::all() {
+                assert!(
+                    unknown_placeholders(entity, &starter_body_html(kind)).is_empty(),
+                    "{} {}",
+                    entity.entity_type,
+                    kind.code()
+                );
+            }
+        }
+        let entity = print_template_entity("a015_wb_orders").unwrap();
+        assert_eq!(
+            unknown_placeholders(entity, "{{sale_id}} {{sale_id}} {{nm_id}}"),
+            vec!["sale_id".to_string()]
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Размер одной месячной «партиции» проекции: строки за месяц в рабочей и архивной таблице.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArchivePartitionDto {
+    /// Месяц `YYYY-MM`.
+    pub month: String,
+    pub hot_rows: i64,
+    pub archive_rows: i64,
+}
+
+/// Архивируемая проекция и её разбиение по месяцам (новые месяцы первыми).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArchiveTableDto {
+    pub table: String,
+    pub archive_table: String,
+    pub hot_rows: i64,
+    pub archive_rows: i64,
+    pub partitions: Vec<ArchivePartitionDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArchiveStatusDto {
+    /// Граница архивации `YYYY-MM`: все строки раньше этого месяца лежат в архиве.
+    /// `None` — архивация ещё не выполнялась.
+    pub boundary: Option<String>,
+    pub tables: Vec<ArchiveTableDto>,
+}
+
+/// Перенести в архив все строки раньше месяца `before_month` (`YYYY-MM`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveMoveRequest {
+    pub before_month: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArchiveMovedTableDto {
+    pub table: String,
+    pub moved_rows: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArchiveMoveResultDto {
+    /// Граница после переноса (не сдвигается назад).
+    pub boundary: String,
+    pub tables: Vec<ArchiveMovedTableDto>,
+}
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+/// Вместо id снимка в сравнении: текущие данные p900.
+pub const SNAPSHOT_CURRENT: &str = "current";
+
+/// Снимок итогов p900 за период.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectionSnapshotDto {
+    pub id: String,
+    pub label: String,
+    /// Первый месяц `YYYY-MM`.
+    pub period_from: String,
+    /// Последний месяц `YYYY-MM` включительно.
+    pub period_to: String,
+    /// Строк «месяц × маркетплейс × SKU».
+    pub row_count: i64,
+    pub revenue: f64,
+    pub created_by: Option<String>,
+    pub created_at: String,
+}
+
+/// POST `/api/sys/projection-snapshots` — зафиксировать итоги за период.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateProjectionSnapshotRequest {
+    pub label: String,
+    pub period_from: String,
+    pub period_to: String,
+}
+
+/// GET `/api/sys/projection-snapshots/compare`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotCompareQuery {
+    /// Id снимка «было».
+    pub base: String,
+    /// Id снимка «стало» или [`SNAPSHOT_CURRENT`].
+    pub target: String,
+    /// Только изменившиеся строки (по умолчанию да).
+    pub only_changed: Option<bool>,
+}
+
+/// Статус строки сравнения.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotDiffStatus {
+    /// Строка появилась после базового снимка
+    Added,
+    /// Строка исчезла
+    Removed,
+    Changed,
+    Same,
+}
+
+impl SnapshotDiffStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Added => "Добавлено",
+            Self::Removed => "Удалено",
+            Self::Changed => "Изменено",
+            Self::Same => "Без изменений",
+        }
+    }
+}
+
+/// Строка сравнения: итоги по месяцу, маркетплейсу и SKU в обоих снимках.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotDiffRowDto {
+    pub period: String,
+    pub marketplace: String,
+    pub sku: String,
+    pub base_qty: f64,
+    pub target_qty: f64,
+    pub base_revenue: f64,
+    pub target_revenue: f64,
+    pub base_cost: f64,
+    pub target_cost: f64,
+    pub status: SnapshotDiffStatus,
+}
+
+/// Итоги месяца в обоих снимках.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotPeriodTotalDto {
+    pub period: String,
+    pub base_revenue: f64,
+    pub target_revenue: f64,
+    pub changed_rows: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotCompareResponse {
+    pub base_label: String,
+    pub target_label: String,
+    /// Сравниваемый период — пересечение периодов снимков.
+    pub period_from: String,
+    pub period_to: String,
+    pub periods: Vec<SnapshotPeriodTotalDto>,
+    /// Строки по убыванию модуля изменения выручки.
+    pub rows: Vec<SnapshotDiffRowDto>,
+    pub changed_rows: usize,
+    /// Строк больше лимита ответа.
+    pub truncated: bool,
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// Квоты организации и использование за текущие сутки / месяц (UTC).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OrgQuotaDto {
+    pub organization_id: String,
+    pub organization_name: String,
+    /// Лимит запросов к API в сутки; `None` — без ограничения.
+    pub requests_per_day: Option<i64>,
+    /// Лимит строк выгрузок в месяц; `None` — без ограничения.
+    pub export_rows_per_month: Option<i64>,
+    pub requests_today: i64,
+    pub export_rows_month: i64,
+    /// Хосты из брендирования, по которым запросы относятся к организации.
+    pub hosts: Vec<String>,
+    pub updated_at: Option<String>,
+    pub updated_by: Option<String>,
+}
+
+impl OrgQuotaDto {
+    /// Доля использования лимита (0..), `None` — лимит не задан.
+    pub fn usage_ratio(used: i64, limit: Option<i64>) -> Option<f64> {
+        limit.map(|limit| {
+            if limit <= 0 {
+                1.0
+            } else {
+                used as f64 / limit as f64
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OrgQuotaUpdateRequest {
+    pub requests_per_day: Option<i64>,
+    pub export_rows_per_month: Option<i64>,
+}
@@ -15,9 +15,31 @@ pub struct RawStorageStatus {
     pub total_mb: f64,
     pub referenced_rows: u64,
     pub unreferenced_rows: u64,
+    /// Прежние версии payload, которые очистка сохраняет для истории документа
+    #[serde(default)]
+    pub history_rows: u64,
     pub by_type: Vec<RawStorageTypeStat>,
 }
 
+/// Сколько последних версий raw JSON одного документа переживают очистку
+/// «без ссылок» и «старше N дней», пока документ ссылается хотя бы на одну из них.
+pub const RAW_VERSIONS_KEPT: u32 = 5;
+
+/// Одна сохранённая версия raw JSON документа (строка `document_raw_storage`
+/// с тем же marketplace + document_type + document_no).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RawPayloadVersionDto {
+    pub id: String,
+    /// Порядковый номер версии, 1 — самая ранняя из сохранившихся
+    pub version: u32,
+    pub fetched_at: String,
+    pub created_at: String,
+    pub raw_hash: String,
+    pub size_bytes: u64,
+    /// На эту версию сейчас ссылается документ
+    pub is_current: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RawStorageSettings {
     pub capture_enabled: bool,
@@ -0,0 +1,155 @@
+//! Решение по физическому возврату (возврат на склад, утилизация, поставщику).
+//!
+//! Сотрудник склада выбирает решение по документу возврата, прикладывает фото
+//! (вложения S3, привязанные к документу) и отправляет на утверждение. Утверждает
+//! пользователь с правом [`crate::shared::access::APPROVE_RETURN_DISPOSITION_SCOPE`];
+//! утверждённое решение пишет движения в `p917_return_stock_ledger`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::projections::p917_return_stock_ledger::dto::ReturnStockLedgerEntryDto;
+use crate::system::s3::S3FileDto;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnDecision {
+    /// Товар годен, возвращается в остаток
+    ReturnToStock,
+    /// Брак, списание с утилизацией
+    Dispose,
+    /// Возврат поставщику (брак производителя)
+    SendToSupplier,
+}
+
+impl ReturnDecision {
+    pub const ALL: [ReturnDecision; 3] = [
+        ReturnDecision::ReturnToStock,
+        ReturnDecision::Dispose,
+        ReturnDecision::SendToSupplier,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::ReturnToStock => "return_to_stock",
+            Self::Dispose => "dispose",
+            Self::SendToSupplier => "send_to_supplier",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ReturnToStock => "Вернуть на склад",
+            Self::Dispose => "Утилизировать",
+            Self::SendToSupplier => "Вернуть поставщику",
+        }
+    }
+
+    /// Изменение остатка склада по строке с количеством `quantity`.
+    pub fn stock_delta(self, quantity: i32) -> i32 {
+        match self {
+            Self::ReturnToStock => quantity,
+            Self::Dispose | Self::SendToSupplier => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnDispositionStatus {
+    /// Решение выбрано, фото можно добавлять
+    Draft,
+    /// Отправлено на утверждение
+    PendingApproval,
+    /// Утверждено, движения записаны
+    Approved,
+    /// Отклонено; можно исправить и отправить снова
+    Rejected,
+}
+
+impl ReturnDispositionStatus {
+    pub const ALL: [ReturnDispositionStatus; 4] = [
+        ReturnDispositionStatus::Draft,
+        ReturnDispositionStatus::PendingApproval,
+        ReturnDispositionStatus::Approved,
+        ReturnDispositionStatus::Rejected,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::PendingApproval => "pending_approval",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Draft => "Черновик",
+            Self::PendingApproval => "На утверждении",
+            Self::Approved => "Утверждено",
+            Self::Rejected => "Отклонено",
+        }
+    }
+
+    /// Решение и комментарий ещё можно менять.
+    pub fn is_editable(self) -> bool {
+        !matches!(self, Self::PendingApproval | Self::Approved)
+    }
+}
+
+/// Запрос `POST /api/return-dispositions`: решение по документу создаётся или
+/// переписывается (пока не отправлено на утверждение).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveReturnDispositionRequest {
+    /// Агрегат документа возврата (`a009_ozon_returns`, `a016_ym_returns`, `a032_wb_returns_claims`)
+    pub entity_type: String,
+    pub document_id: String,
+    pub decision: ReturnDecision,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// Запрос `POST /api/return-dispositions/:id/reject`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectReturnDispositionRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReturnDispositionDto {
+    pub id: String,
+    pub entity_type: String,
+    pub document_id: String,
+    pub decision: ReturnDecision,
+    pub status: ReturnDispositionStatus,
+    pub comment: Option<String>,
+    /// Логин автора решения
+    pub created_by: String,
+    pub created_at: String,
+    pub submitted_at: Option<String>,
+    /// Логин утвердившего или отклонившего
+    pub decided_by: Option<String>,
+    pub decided_at: Option<String>,
+    pub reject_reason: Option<String>,
+}
+
+/// Ответ `GET /api/return-dispositions?entity_type=&document_id=`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReturnDispositionView {
+    pub disposition: Option<ReturnDispositionDto>,
+    /// Фото (вложения-изображения документа возврата)
+    pub photos: Vec<S3FileDto>,
+    /// Движения утверждённого решения
+    pub ledger: Vec<ReturnStockLedgerEntryDto>,
+    /// Может ли текущий пользователь утверждать решения
+    pub can_approve: bool,
+}
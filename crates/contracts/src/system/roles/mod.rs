@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::shared::access::ScopeAccess;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Role {
     pub id: String,
@@ -33,3 +35,25 @@ pub struct RoleScopeAccess {
 pub struct ScopeInfo {
     pub scope_id: String,
 }
+
+/// Additional (non-primary) roles assigned to a user.
+/// GET/PUT /api/system/users/:id/roles — PUT replaces the whole set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserRolesDto {
+    pub role_ids: Vec<String>,
+}
+
+/// Effective permissions of a role for the admin "view as role" preview.
+/// GET /api/system/roles/:id/preview — read-only, nothing is persisted.
+///
+/// For a system role `primary_role` is the role itself. A custom role is
+/// previewed as it would apply to a new user: the default `viewer` primary
+/// role plus the custom role's grants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RolePreview {
+    pub role_id: String,
+    pub role_code: String,
+    pub role_name: String,
+    pub primary_role: String,
+    pub scopes: Vec<ScopeAccess>,
+}
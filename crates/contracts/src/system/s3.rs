@@ -10,6 +10,8 @@ pub enum S3FileCategory {
     Backups,
     #[serde(rename = "conference_audio")]
     ConferenceAudio,
+    #[serde(rename = "exports")]
+    Exports,
     #[serde(rename = "other")]
     Other,
 }
@@ -27,6 +29,7 @@ impl S3FileCategory {
             Self::Plugins => "plugins",
             Self::Backups => "backups",
             Self::ConferenceAudio => "conference_audio",
+            Self::Exports => "exports",
             Self::Other => "other",
         }
     }
@@ -37,6 +40,7 @@ impl S3FileCategory {
             Self::Plugins => "Плагины",
             Self::Backups => "Архивы БД",
             Self::ConferenceAudio => "Аудио конференций",
+            Self::Exports => "Выгрузки",
             Self::Other => "Другое",
         }
     }
@@ -47,6 +51,7 @@ impl S3FileCategory {
             Self::Plugins,
             Self::Backups,
             Self::ConferenceAudio,
+            Self::Exports,
             Self::Other,
         ]
     }
@@ -59,6 +64,7 @@ impl From<&str> for S3FileCategory {
             "plugins" => Self::Plugins,
             "backups" => Self::Backups,
             "conference_audio" => Self::ConferenceAudio,
+            "exports" => Self::Exports,
             "other" => Self::Other,
             _ => Self::Other,
         }
@@ -78,6 +84,11 @@ pub struct S3FileDto {
     pub uploaded_by_user_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// Тип документа, к которому приложен файл (`a023_purchase_of_goods`, ...)
+    #[serde(default)]
+    pub document_type: Option<String>,
+    #[serde(default)]
+    pub document_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -90,3 +101,24 @@ pub struct S3FileListResponse {
 pub struct S3UploadResponse {
     pub file: S3FileDto,
 }
+
+/// Найденное вложение: совпадение в распознанном тексте файла.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3TextSearchHit {
+    pub file_id: String,
+    pub original_filename: String,
+    pub category: S3FileCategory,
+    pub document_type: Option<String>,
+    pub document_id: Option<String>,
+    /// Фрагмент текста, совпадения выделены `[` `]`
+    pub snippet: String,
+    pub created_at: String,
+}
+
+/// GET `/api/sys/s3/search?q=` — полнотекстовый поиск по вложениям.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3TextSearchResponse {
+    pub items: Vec<S3TextSearchHit>,
+    /// Файлов, ожидающих распознавания (в поиск ещё не попали)
+    pub pending_files: i64,
+}
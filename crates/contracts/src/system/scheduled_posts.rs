@@ -0,0 +1,76 @@
+//! Отложенное проведение документов.
+//!
+//! Пользователь ставит документ в очередь на проведение к указанному времени
+//! (например, когда данные периода будут полными). Задание выполняет сервер;
+//! пока оно ожидает, в шапке документа видно «В очереди на проведение: …».
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledPostStatus {
+    /// Ждёт наступления `post_at`
+    Pending,
+    /// Документ проведён
+    Done,
+    /// Отменено пользователем или заменено новым заданием
+    Cancelled,
+    /// Проведение завершилось ошибкой
+    Failed,
+}
+
+impl ScheduledPostStatus {
+    pub const ALL: [ScheduledPostStatus; 4] = [
+        ScheduledPostStatus::Pending,
+        ScheduledPostStatus::Done,
+        ScheduledPostStatus::Cancelled,
+        ScheduledPostStatus::Failed,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Done => "done",
+            Self::Cancelled => "cancelled",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pending => "В очереди",
+            Self::Done => "Проведён",
+            Self::Cancelled => "Отменено",
+            Self::Failed => "Ошибка",
+        }
+    }
+}
+
+/// Запрос `POST /api/scheduled-posts`: ожидающее задание по документу заменяется новым.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulePostRequest {
+    /// Агрегат документа (`a012_wb_sales`, `a013_ym_order`, `a016_ym_returns`)
+    pub entity_type: String,
+    pub document_id: String,
+    /// Время проведения, RFC3339
+    pub post_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScheduledPostDto {
+    pub id: String,
+    pub entity_type: String,
+    pub document_id: String,
+    /// Время проведения, UTC RFC3339
+    pub post_at: String,
+    pub status: ScheduledPostStatus,
+    pub error: Option<String>,
+    /// Логин поставившего в очередь
+    pub created_by: String,
+    pub created_at: String,
+    pub finished_at: Option<String>,
+}
@@ -1,7 +1,9 @@
 //! Единый маппинг ImportProgress (u501–u504) → [`super::progress::TaskProgress`] / [`TaskProgressResponse`].
 //! Используется бэкендом (`TaskManager::get_progress`) и фронтом (u504 и др.) без дублирования правил.
 
-use super::progress::{TaskProgress, TaskProgressDetail, TaskProgressResponse, TaskStatus};
+use super::progress::{
+    TaskAggregateSummary, TaskProgress, TaskProgressDetail, TaskProgressResponse, TaskStatus,
+};
 use crate::usecases::u501_import_from_ut::progress::{
     AggregateImportStatus as A501, ImportProgress as P501, ImportStatus as S501,
 };
@@ -138,6 +140,8 @@ pub struct NormalizedImportSnapshot {
     pub http_request_count: i32,
     pub http_bytes_sent: i64,
     pub http_bytes_received: i64,
+    /// Повторы запросов после временных сбоев API (u502 / u504).
+    pub http_retry_count: i32,
 }
 
 fn import_status_to_task(s501: S501) -> TaskStatus {
@@ -205,6 +209,7 @@ pub fn normalize_u501(p: &P501) -> NormalizedImportSnapshot {
         http_request_count: 0,
         http_bytes_sent: 0,
         http_bytes_received: 0,
+        http_retry_count: 0,
     }
 }
 
@@ -228,6 +233,7 @@ pub fn normalize_u502(p: &P502) -> NormalizedImportSnapshot {
         http_request_count: 0,
         http_bytes_sent: 0,
         http_bytes_received: 0,
+        http_retry_count: p.http_retry_count,
     }
 }
 
@@ -251,6 +257,7 @@ pub fn normalize_u503(p: &P503) -> NormalizedImportSnapshot {
         http_request_count: 0,
         http_bytes_sent: 0,
         http_bytes_received: 0,
+        http_retry_count: 0,
     }
 }
 
@@ -304,6 +311,7 @@ pub fn normalize_u504(p: &P504, aggregate_filter: Option<&str>) -> NormalizedImp
         http_request_count: p.http_request_count,
         http_bytes_sent: p.http_bytes_sent,
         http_bytes_received: p.http_bytes_received,
+        http_retry_count: p.http_retry_count,
     }
 }
 
@@ -476,6 +484,17 @@ pub fn task_progress_from_normalized(
     } else {
         Some(n.error_messages.clone())
     };
+    let aggregates = n
+        .aggregates
+        .iter()
+        .map(|a| TaskAggregateSummary {
+            index: a.index.clone(),
+            name: a.name.clone(),
+            inserted: a.inserted,
+            updated: a.updated,
+            errors: a.errors,
+        })
+        .collect();
 
     TaskProgress {
         session_id: n.session_id,
@@ -493,6 +512,8 @@ pub fn task_progress_from_normalized(
         http_request_count: Some(n.http_request_count),
         http_bytes_sent: Some(n.http_bytes_sent),
         http_bytes_received: Some(n.http_bytes_received),
+        http_retry_count: Some(n.http_retry_count),
+        aggregates: Some(aggregates),
     }
 }
 
@@ -40,6 +40,75 @@ pub struct ExternalApiInfo {
     pub rate_limit_desc: &'static str,
 }
 
+/// Класс параллельности: сколько задач одного класса воркер выполняет одновременно.
+/// Ограничение действует поверх `write_tables` — тяжёлые импорты не душат БД
+/// даже при непересекающихся таблицах.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskConcurrencyClass {
+    /// Частый опрос заказов (короткие запуски)
+    Polling,
+    /// Загрузка справочников и операций маркетплейсов
+    MarketplaceImport,
+    /// Финансовые отчёты и документы — самые тяжёлые записи в БД
+    FinanceImport,
+    /// Пересборка и уплотнение проекций
+    ProjectionRebuild,
+    /// Аналитические расчёты по накопленным данным
+    Analytics,
+    /// Почта и база знаний (в основном внешние API)
+    Communication,
+}
+
+impl TaskConcurrencyClass {
+    pub const ALL: [Self; 6] = [
+        Self::Polling,
+        Self::MarketplaceImport,
+        Self::FinanceImport,
+        Self::ProjectionRebuild,
+        Self::Analytics,
+        Self::Communication,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Polling => "polling",
+            Self::MarketplaceImport => "marketplace_import",
+            Self::FinanceImport => "finance_import",
+            Self::ProjectionRebuild => "projection_rebuild",
+            Self::Analytics => "analytics",
+            Self::Communication => "communication",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.code() == code)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Polling => "Опрос заказов",
+            Self::MarketplaceImport => "Импорт маркетплейсов",
+            Self::FinanceImport => "Финансовый импорт",
+            Self::ProjectionRebuild => "Пересборка проекций",
+            Self::Analytics => "Аналитика",
+            Self::Communication => "Почта и база знаний",
+        }
+    }
+
+    /// Максимум одновременно выполняемых задач класса.
+    pub fn max_concurrent(self) -> usize {
+        match self {
+            Self::Polling => 3,
+            Self::MarketplaceImport => 2,
+            Self::FinanceImport => 1,
+            Self::ProjectionRebuild => 4,
+            Self::Analytics => 2,
+            Self::Communication => 2,
+        }
+    }
+}
+
 /// Статические метаданные типа задачи для бэкенда (описание для человека и LLM)
 #[derive(Debug, Clone)]
 pub struct TaskMetadata {
@@ -51,6 +120,8 @@ pub struct TaskMetadata {
     /// Business tables modified by this task. Scheduler uses this list to avoid concurrent
     /// writers. Scheduler bookkeeping and shared raw-storage tables are intentionally omitted.
     pub write_tables: &'static [&'static str],
+    /// Класс параллельности — лимит одновременных запусков задач этого класса.
+    pub concurrency_class: TaskConcurrencyClass,
     /// Schema for the task's config_json — drives the UI editor.
     /// Empty slice means no structured editor; raw JSON textarea is shown.
     pub config_fields: &'static [TaskConfigField],
@@ -98,6 +169,7 @@ pub struct TaskMetadataDto {
     pub external_apis: Vec<ExternalApiInfoDto>,
     pub constraints: Vec<String>,
     pub write_tables: Vec<String>,
+    pub concurrency_class: TaskConcurrencyClass,
     /// Structured config schema — empty vec means show raw JSON textarea.
     pub config_fields: Vec<TaskConfigFieldDto>,
     pub max_duration_seconds: u64,
@@ -120,6 +192,7 @@ impl From<&TaskMetadata> for TaskMetadataDto {
                 .collect(),
             constraints: m.constraints.iter().map(|s| s.to_string()).collect(),
             write_tables: m.write_tables.iter().map(|s| s.to_string()).collect(),
+            concurrency_class: m.concurrency_class,
             max_duration_seconds: m.max_duration_seconds,
             config_fields: m
                 .config_fields
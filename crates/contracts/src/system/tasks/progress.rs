@@ -146,6 +146,23 @@ pub struct TaskProgress {
     /// Суммарный размер полученных тел ответов, байт.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub http_bytes_received: Option<i64>,
+    /// Повторы запросов после временных сбоев API (429/5xx/обрыв связи).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_retry_count: Option<i32>,
+    /// Итоги по типам документов (импорты u501–u504) — для сводки запуска.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aggregates: Option<Vec<TaskAggregateSummary>>,
+}
+
+/// Итог импорта одного типа документов внутри запуска.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskAggregateSummary {
+    /// Код агрегата (`a012_wb_sales` и т.п.).
+    pub index: String,
+    pub name: String,
+    pub inserted: i32,
+    pub updated: i32,
+    pub errors: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -173,6 +190,8 @@ pub struct TaskProgressResponse {
     pub http_bytes_sent: Option<i64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub http_bytes_received: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_retry_count: Option<i32>,
 }
 
 impl Default for TaskProgressResponse {
@@ -193,6 +212,7 @@ impl Default for TaskProgressResponse {
             http_request_count: None,
             http_bytes_sent: None,
             http_bytes_received: None,
+            http_retry_count: None,
         }
     }
 }
@@ -215,6 +235,7 @@ impl From<TaskProgress> for TaskProgressResponse {
             http_request_count: p.http_request_count,
             http_bytes_sent: p.http_bytes_sent,
             http_bytes_received: p.http_bytes_received,
+            http_retry_count: p.http_retry_count,
         }
     }
 }
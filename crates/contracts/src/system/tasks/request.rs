@@ -52,3 +52,12 @@ pub struct SchedulerStatusDto {
 fn default_config_enabled() -> bool {
     true
 }
+
+/// Подписка текущего администратора на письмо-сводку после плановых импортов.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportSummarySubscriptionDto {
+    pub subscribed: bool,
+    /// Адрес доставки из профиля; без него подписка не сработает.
+    #[serde(default)]
+    pub email: Option<String>,
+}
@@ -47,3 +47,10 @@ impl From<ScheduledTask> for ScheduledTaskResponse {
 pub struct ScheduledTaskListResponse {
     pub tasks: Vec<ScheduledTaskResponse>,
 }
+
+/// Предпросмотр cron-расписания: нормализованное выражение и ближайшие запуски.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleCronPreviewResponse {
+    pub schedule_cron: String,
+    pub next_runs: Vec<chrono::DateTime<chrono::Utc>>,
+}
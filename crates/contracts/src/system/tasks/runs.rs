@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::metadata::TaskConcurrencyClass;
 use super::progress::TaskProgressResponse;
 
 /// Запись о конкретном запуске регламентного задания
@@ -29,6 +30,8 @@ pub struct TaskRun {
     pub http_bytes_sent: Option<i64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub http_bytes_received: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_retry_count: Option<i64>,
 }
 
 /// Ответ со списком запусков конкретной задачи
@@ -72,3 +75,25 @@ pub struct LiveMemoryProgressItem {
 pub struct LiveMemoryProgressResponse {
     pub items: Vec<LiveMemoryProgressItem>,
 }
+
+/// Задача, занимающая слот класса параллельности.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcurrencySlotItem {
+    pub session_id: String,
+    pub task_label: String,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Загрузка класса параллельности: лимит и выполняющиеся задачи.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcurrencyClassStatus {
+    pub class: TaskConcurrencyClass,
+    pub label: String,
+    pub limit: usize,
+    pub running: Vec<ConcurrencySlotItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcurrencyStatusResponse {
+    pub classes: Vec<ConcurrencyClassStatus>,
+}
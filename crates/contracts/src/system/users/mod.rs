@@ -1,5 +1,9 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
+/// За сколько дней до окончания срока доступа напоминать пользователю и администраторам.
+pub const EXPIRY_REMINDER_DAYS: i64 = 7;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub id: String,
@@ -13,6 +17,11 @@ pub struct User {
     pub updated_at: String,
     pub last_login_at: Option<String>,
     pub created_by: Option<String>,
+    /// Срок действия учётной записи (`YYYY-MM-DD`, включительно); `None` — без ограничения.
+    #[serde(default)]
+    pub active_from: Option<String>,
+    #[serde(default)]
+    pub active_until: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +32,10 @@ pub struct CreateUserDto {
     pub full_name: Option<String>,
     pub is_admin: bool,
     pub primary_role_code: String,
+    #[serde(default)]
+    pub active_from: Option<String>,
+    #[serde(default)]
+    pub active_until: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +46,10 @@ pub struct UpdateUserDto {
     pub is_active: bool,
     pub is_admin: bool,
     pub primary_role_code: String,
+    #[serde(default)]
+    pub active_from: Option<String>,
+    #[serde(default)]
+    pub active_until: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,3 +58,125 @@ pub struct ChangePasswordDto {
     pub old_password: Option<String>, // None if admin changing someone else's password
     pub new_password: String,
 }
+
+/// Положение текущего дня относительно срока действия учётной записи.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessWindow {
+    /// Срок ещё не начался (`active_from` в будущем)
+    NotStarted,
+    Active,
+    /// Срок истёк (`active_until` в прошлом)
+    Expired,
+}
+
+fn parse_date(value: Option<&str>) -> Option<NaiveDate> {
+    value.and_then(|v| NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d").ok())
+}
+
+/// Где сегодняшний день относительно срока доступа. Пустые границы не ограничивают.
+pub fn access_window(
+    active_from: Option<&str>,
+    active_until: Option<&str>,
+    today: NaiveDate,
+) -> AccessWindow {
+    if parse_date(active_from).is_some_and(|from| today < from) {
+        return AccessWindow::NotStarted;
+    }
+    if parse_date(active_until).is_some_and(|until| today > until) {
+        return AccessWindow::Expired;
+    }
+    AccessWindow::Active
+}
+
+/// Сколько дней доступа осталось (0 — последний день, отрицательное — срок истёк).
+pub fn days_until_expiry(active_until: Option<&str>, today: NaiveDate) -> Option<i64> {
+    parse_date(active_until).map(|until| (until - today).num_days())
+}
+
+/// Проверка срока из формы: даты в формате `YYYY-MM-DD`, начало не позже окончания.
+pub fn validate_access_period(
+    active_from: Option<&str>,
+    active_until: Option<&str>,
+) -> Result<(), String> {
+    for value in [active_from, active_until].into_iter().flatten() {
+        if parse_date(Some(value)).is_none() {
+            return Err(format!("Некорректная дата срока доступа: {value}"));
+        }
+    }
+    if let (Some(from), Some(until)) = (parse_date(active_from), parse_date(active_until)) {
+        if from > until {
+            return Err("Начало срока доступа позже окончания".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Строка отчёта «Истекающие учётные записи».
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpiringUserDto {
+    pub id: String,
+    pub username: String,
+    pub full_name: Option<String>,
+    pub email: Option<String>,
+    pub is_active: bool,
+    pub active_until: String,
+    /// Отрицательное — срок уже истёк
+    pub days_left: i64,
+    pub last_login_at: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn access_window_bounds_are_inclusive() {
+        let (from, until) = (Some("2026-03-01"), Some("2026-03-31"));
+        assert_eq!(
+            access_window(from, until, date("2026-02-28")),
+            AccessWindow::NotStarted
+        );
+        assert_eq!(
+            access_window(from, until, date("2026-03-01")),
+            AccessWindow::Active
+        );
+        assert_eq!(
+            access_window(from, until, date("2026-03-31")),
+            AccessWindow::Active
+        );
+        assert_eq!(
+            access_window(from, until, date("2026-04-01")),
+            AccessWindow::Expired
+        );
+        assert_eq!(
+            access_window(None, None, date("2026-04-01")),
+            AccessWindow::Active
+        );
+    }
+
+    #[test]
+    fn days_left_counts_to_last_day() {
+        assert_eq!(
+            days_until_expiry(Some("2026-03-31"), date("2026-03-24")),
+            Some(7)
+        );
+        assert_eq!(
+            days_until_expiry(Some("2026-03-31"), date("2026-04-02")),
+            Some(-2)
+        );
+        assert_eq!(days_until_expiry(None, date("2026-04-02")), None);
+    }
+
+    #[test]
+    fn access_period_validation() {
+        assert!(validate_access_period(Some("2026-03-01"), Some("2026-03-31")).is_ok());
+        assert!(validate_access_period(None, Some("2026-03-31")).is_ok());
+        assert!(validate_access_period(Some("2026-04-01"), Some("2026-03-31")).is_err());
+        assert!(validate_access_period(None, Some("31.03.2026")).is_err());
+    }
+}
@@ -20,6 +20,10 @@ pub struct ImportProgress {
     pub total_updated: i32,
     pub total_errors: i32,
 
+    /// Повторы запросов к OZON API после 429/5xx/обрыва связи.
+    #[serde(default)]
+    pub http_retry_count: i32,
+
     /// Ошибки импорта
     pub errors: Vec<ImportError>,
 }
@@ -88,6 +92,7 @@ impl ImportProgress {
             total_inserted: 0,
             total_updated: 0,
             total_errors: 0,
+            http_retry_count: 0,
             errors: Vec::new(),
         }
     }
@@ -27,6 +27,16 @@ pub struct ImportProgress {
     pub http_bytes_sent: i64,
     #[serde(default)]
     pub http_bytes_received: i64,
+    /// Повторы запросов после 429/5xx/обрыва связи (входят в `http_request_count`).
+    #[serde(default)]
+    pub http_retry_count: i32,
+
+    /// Raw JSON, записанные в document_raw_storage за сессию
+    #[serde(default)]
+    pub raw_payloads_stored: i64,
+    /// Повторные выгрузки с тем же sha256 — новая запись не создавалась
+    #[serde(default)]
+    pub raw_payloads_deduplicated: i64,
 
     /// Ошибки импорта
     pub errors: Vec<ImportError>,
@@ -99,6 +109,9 @@ impl ImportProgress {
             http_request_count: 0,
             http_bytes_sent: 0,
             http_bytes_received: 0,
+            http_retry_count: 0,
+            raw_payloads_stored: 0,
+            raw_payloads_deduplicated: 0,
             errors: Vec::new(),
         }
     }
@@ -2,6 +2,7 @@ pub mod events;
 pub mod progress;
 pub mod request;
 pub mod response;
+pub mod suggestions;
 
 pub use events::MatchEvent;
 pub use progress::MatchProgress;
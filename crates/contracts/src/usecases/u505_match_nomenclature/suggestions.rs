@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+
+/// Правило, по которому найден кандидат номенклатуры для товара маркетплейса
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchRule {
+    /// Штрихкод товара МП есть среди штрихкодов номенклатуры (p901)
+    Barcode,
+    /// Артикулы совпадают без учёта регистра
+    Exact,
+    /// Совпадают после удаления пробелов, дефисов и прочих разделителей
+    Normalized,
+    /// Один артикул — начало другого (размер/цвет в хвосте артикула МП)
+    Prefix,
+    /// Похожие наименования (доля общих слов); оценка умножается на схожесть
+    Name,
+}
+
+impl MatchRule {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Barcode => "barcode",
+            Self::Exact => "exact",
+            Self::Normalized => "normalized",
+            Self::Prefix => "prefix",
+            Self::Name => "name",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "barcode" => Some(Self::Barcode),
+            "exact" => Some(Self::Exact),
+            "normalized" => Some(Self::Normalized),
+            "prefix" => Some(Self::Prefix),
+            "name" => Some(Self::Name),
+            _ => None,
+        }
+    }
+
+    /// Базовая оценка правила, пока по бренду нет решений пользователей
+    pub fn base_score(&self) -> f64 {
+        match self {
+            Self::Barcode => 0.98,
+            Self::Exact => 0.95,
+            Self::Normalized => 0.8,
+            Self::Prefix => 0.5,
+            Self::Name => 0.6,
+        }
+    }
+}
+
+/// Параметры списка предложений (GET /api/u505/suggestions)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchSuggestionQuery {
+    /// Фильтр по маркетплейсу (a005.id)
+    #[serde(default)]
+    pub marketplace_id: Option<String>,
+    /// Минимальная оценка кандидата (по умолчанию 0.3)
+    #[serde(default)]
+    pub min_score: Option<f64>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub offset: Option<usize>,
+}
+
+/// Кандидат номенклатуры для товара
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchCandidateDto {
+    pub nomenclature_ref: String,
+    pub article: String,
+    pub description: String,
+    pub rule: MatchRule,
+    /// Оценка 0..1 с учётом решений пользователей по бренду и правилу
+    pub score: f64,
+}
+
+/// Товар без связи с номенклатурой и кандидаты для него (по убыванию оценки)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchSuggestionDto {
+    pub marketplace_product_ref: String,
+    pub marketplace_ref: String,
+    pub article: String,
+    pub description: String,
+    pub brand: Option<String>,
+    pub candidates: Vec<MatchCandidateDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchSuggestionListResponse {
+    pub items: Vec<MatchSuggestionDto>,
+    /// Всего товаров с кандидатами (до пагинации)
+    pub total: usize,
+}
+
+/// Решение пользователя по паре «товар — номенклатура»
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchDecisionDto {
+    pub marketplace_product_ref: String,
+    pub nomenclature_ref: String,
+    /// true — принять (связь устанавливается), false — отклонить
+    pub accept: bool,
+}
+
+/// Пакет решений (POST /api/u505/suggestions/decisions)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchDecisionRequest {
+    pub decisions: Vec<MatchDecisionDto>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchDecisionResponse {
+    pub accepted: usize,
+    pub rejected: usize,
+    /// Решения, которые не удалось применить (товар/номенклатура не найдены и т.п.)
+    pub errors: Vec<String>,
+}
+
+/// Порог «уверенного» предложения для массового принятия по умолчанию
+pub const HIGH_CONFIDENCE_SCORE: f64 = 0.9;
+
+/// Массовое принятие уверенных предложений
+/// (POST /api/u505/suggestions/accept-high-confidence).
+/// Принимается лучший кандидат товара, если его оценка не ниже порога, а остальные
+/// кандидаты того же товара — ниже порога (иначе выбор неоднозначен).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchAutoAcceptRequest {
+    #[serde(default)]
+    pub marketplace_id: Option<String>,
+    /// Порог оценки (по умолчанию [`HIGH_CONFIDENCE_SCORE`])
+    #[serde(default)]
+    pub min_score: Option<f64>,
+}
+
+/// Шкала отчёта точности
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchAccuracyScale {
+    Day,
+    #[default]
+    Week,
+    Month,
+}
+
+/// Параметры отчёта точности (GET /api/u505/suggestions/accuracy)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchAccuracyQuery {
+    #[serde(default)]
+    pub scale: MatchAccuracyScale,
+    /// YYYY-MM-DD, включительно
+    #[serde(default)]
+    pub date_from: Option<String>,
+}
+
+/// Точность предложений: доля принятых среди всех решённых
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchAccuracyPoint {
+    /// Начало периода (YYYY-MM-DD) или код правила — в зависимости от разреза
+    pub key: String,
+    pub accepted: i64,
+    pub rejected: i64,
+    /// accepted / (accepted + rejected); None — решений нет
+    pub precision: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchAccuracyReport {
+    /// Динамика по периодам
+    pub periods: Vec<MatchAccuracyPoint>,
+    /// Итог по правилам за тот же интервал
+    pub by_rule: Vec<MatchAccuracyPoint>,
+}
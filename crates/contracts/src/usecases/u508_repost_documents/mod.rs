@@ -1,13 +1,17 @@
 pub mod aggregate;
 pub mod aggregate_request;
+pub mod period_request;
 pub mod progress;
 pub mod projection;
+pub mod rebuild_request;
 pub mod request;
 pub mod response;
 
 pub use aggregate::AggregateOption;
 pub use aggregate_request::AggregateRepostRequest;
+pub use period_request::PeriodRepostRequest;
 pub use progress::RepostProgress;
 pub use projection::ProjectionOption;
+pub use rebuild_request::ProjectionRebuildRequest;
 pub use request::RepostRequest;
 pub use response::RepostResponse;
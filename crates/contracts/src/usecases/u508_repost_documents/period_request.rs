@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Полный цикл «распровести → провести» всех документов периода одной сессией.
+///
+/// Агрегаты обрабатываются в фиксированном порядке зависимостей (себестоимость →
+/// заказы → продажи → реклама → реализация), независимо от порядка в запросе.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodRepostRequest {
+    pub date_from: String,
+    pub date_to: String,
+    /// Ключи агрегатов из `/api/u508/repost/aggregates`; пусто = все поддерживаемые.
+    #[serde(default)]
+    pub aggregate_keys: Vec<String>,
+    /// Брать только проведённые документы (непроведённые не трогаем).
+    #[serde(default = "default_only_posted")]
+    pub only_posted: bool,
+    #[serde(default)]
+    pub connection_mp_refs: Vec<String>,
+}
+
+fn default_only_posted() -> bool {
+    true
+}
@@ -28,6 +28,9 @@ pub struct RepostProgress {
     pub max_doc_ms: i64,
     #[serde(default)]
     pub max_doc_id: Option<String>,
+    /// Пересборка проекции с нуля: удалено строк проекции при очистке периода.
+    #[serde(default)]
+    pub truncated_rows: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -61,6 +64,7 @@ impl RepostProgress {
             total_post_ms: 0,
             max_doc_ms: 0,
             max_doc_id: None,
+            truncated_rows: 0,
         }
     }
 }
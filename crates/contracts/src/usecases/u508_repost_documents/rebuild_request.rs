@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// Проекции, которые можно пересобрать с нуля: `(ключ, название)`.
+pub const REBUILDABLE_PROJECTIONS: &[(&str, &str)] = &[
+    ("p900", "p900 — MP Sales Register"),
+    ("p904", "p904 — Sales Data"),
+];
+
+/// Пересборка проекции с нуля за период
+/// (`POST /api/projections/rebuild?projection=p900&from=...&to=...`).
+///
+/// Строки проекции за период удаляются, затем перепроводятся все проведённые
+/// документы-источники периода; прогресс — по обычной сессии u508. p904 грузится
+/// массово через стейджинг и заменяет период одной транзакцией.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectionRebuildRequest {
+    /// Ключ из `REBUILDABLE_PROJECTIONS`
+    pub projection: String,
+    pub from: String,
+    pub to: String,
+}
@@ -0,0 +1,9 @@
+pub mod progress;
+pub mod report;
+pub mod request;
+pub mod response;
+
+pub use progress::{LinkProgress, LinkStatus};
+pub use report::{UnmatchedSkuReport, UnmatchedSkuRow};
+pub use request::LinkRequest;
+pub use response::{LinkResponse, LinkStartStatus};
@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Прогресс привязки SKU
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkProgress {
+    pub session_id: String,
+    pub status: LinkStatus,
+    /// Документов a014 под отбором
+    pub total: Option<i32>,
+    /// Обработано документов
+    pub processed: i32,
+    /// Документов, в которых изменились ссылки
+    pub documents_updated: i32,
+    /// Строк, получивших ссылку на a007
+    pub items_linked: i32,
+    /// Строк, для которых товар не найден
+    pub items_unmatched: i32,
+    pub errors: i32,
+    #[serde(default)]
+    pub error_messages: Vec<String>,
+    pub current_item: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkStatus {
+    InProgress,
+    Completed,
+    CompletedWithErrors,
+    Failed,
+}
+
+impl LinkProgress {
+    pub fn new(session_id: String, total: Option<i32>) -> Self {
+        Self {
+            session_id,
+            status: LinkStatus::InProgress,
+            total,
+            processed: 0,
+            documents_updated: 0,
+            items_linked: 0,
+            items_unmatched: 0,
+            errors: 0,
+            error_messages: Vec::new(),
+            current_item: None,
+            started_at: Utc::now(),
+            completed_at: None,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// SKU Ozon из транзакций, для которого не найден товар маркетплейса (a007).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UnmatchedSkuRow {
+    pub connection_id: String,
+    pub sku: i64,
+    /// Название товара из транзакции
+    pub name: String,
+    /// Строк транзакций без ссылки
+    pub items: i64,
+    /// Документов a014 с этим SKU
+    pub documents: i64,
+    /// Последняя дата операции
+    pub last_operation_date: Option<String>,
+}
+
+/// Отчёт по несопоставленным SKU (GET `/api/u509/unmatched`), самые частые первыми.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmatchedSkuReport {
+    pub rows: Vec<UnmatchedSkuRow>,
+    /// Всего строк транзакций без ссылки
+    pub total_items: i64,
+    /// Строк отчёта больше лимита
+    pub truncated: bool,
+}
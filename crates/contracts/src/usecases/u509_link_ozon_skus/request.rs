@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// Ретроспективная привязка строк транзакций Ozon (a014) к товарам маркетплейса (a007).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LinkRequest {
+    /// Подключение Ozon; пусто — все подключения
+    #[serde(default)]
+    pub connection_id: Option<String>,
+    /// Дата операции с (`YYYY-MM-DD`)
+    #[serde(default)]
+    pub date_from: Option<String>,
+    /// Дата операции по (`YYYY-MM-DD`, включительно)
+    #[serde(default)]
+    pub date_to: Option<String>,
+    /// Перепривязать и уже связанные строки (после исправления карточек a007)
+    #[serde(default)]
+    pub relink_existing: bool,
+}
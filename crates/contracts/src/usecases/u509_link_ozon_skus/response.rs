@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Ответ на запуск привязки
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkResponse {
+    pub session_id: String,
+    pub status: LinkStartStatus,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkStartStatus {
+    Started,
+    Failed,
+}
@@ -0,0 +1,65 @@
+//! Criterion-бенчмарки конвейера проведения a012 через HTTP API.
+//!
+//! Один backend на временной БД на весь прогон; сравнивать цифры имеет смысл
+//! только между сборками на одной машине (`cargo bench -p e2e -- --save-baseline`).
+//! Требует собранного backend, как и остальные сценарии крейта.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use e2e::api::ApiClient;
+use e2e::backend::TestBackend;
+use e2e::{fixtures, synthetic};
+
+fn posting_benches(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().expect("tokio runtime");
+
+    let (backend, api, ids) = runtime.block_on(async {
+        let backend = TestBackend::start().await.expect("start backend");
+        let db = backend.db().await.expect("open test db");
+        fixtures::load_wb_sale(&db).await.expect("load fixtures");
+        let ids = synthetic::generate_wb_sales(&db, 50)
+            .await
+            .expect("generate documents");
+        db.close().await;
+        let api = ApiClient::login(backend.base_url(), "admin", "admin")
+            .await
+            .expect("login");
+        (backend, api, ids)
+    });
+
+    let mut group = c.benchmark_group("a012_posting");
+
+    // Перепроведение уже проведённого документа: delete-by-registrator + insert
+    // во все проекции — основной путь при массовых перепроведениях u508.
+    let sale_id = ids[0].clone();
+    runtime
+        .block_on(api.post_wb_sale(&sale_id))
+        .expect("initial post");
+    group.bench_function("repost_one", |b| {
+        b.to_async(&runtime)
+            .iter(|| async { api.post_wb_sale(&sale_id).await.expect("post") })
+    });
+
+    // Полный цикл «распровести → провести» по кругу из 50 разных документов.
+    let mut next = 0usize;
+    group.bench_function("unpost_post_cycle", |b| {
+        b.to_async(&runtime).iter(|| {
+            let id = ids[next % ids.len()].clone();
+            next += 1;
+            let api = api.clone();
+            async move {
+                api.unpost_wb_sale(&id).await.expect("unpost");
+                api.post_wb_sale(&id).await.expect("post");
+            }
+        })
+    });
+
+    group.finish();
+    runtime.block_on(backend.stop()).expect("stop backend");
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(30);
+    targets = posting_benches
+}
+criterion_main!(benches);
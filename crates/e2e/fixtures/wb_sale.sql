@@ -0,0 +1,39 @@
+-- Минимальный набор данных для сценария «открыть продажу WB → провести → проверить проекцию»:
+-- маркетплейс WB, организация, кабинет WB и одна непроведённая продажа a012.
+-- Применяется к временной БД уже после того, как backend прогнал миграции.
+
+INSERT INTO a005_marketplace (id, code, description, url, marketplace_type, created_at, updated_at)
+VALUES ('e2e00005-0000-4000-8000-000000000005', 'mp-wb', 'Wildberries', 'https://www.wildberries.ru',
+        'Wildberries', strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
+
+INSERT INTO a002_organization (id, code, description, full_name, inn, created_at, updated_at)
+VALUES ('e2e00002-0000-4000-8000-000000000002', 'E2E-ORG', 'E2E Организация',
+        'ООО «E2E Организация»', '7700000000', strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
+
+INSERT INTO a006_connection_mp (
+    id, code, description, marketplace, organization, organization_ref, api_key,
+    is_used, created_at, updated_at
+) VALUES (
+    'e2e00006-0000-4000-8000-000000000006', 'E2E-WB', 'E2E кабинет WB',
+    'e2e00005-0000-4000-8000-000000000005', 'E2E Организация',
+    'e2e00002-0000-4000-8000-000000000002', 'e2e-not-a-real-key', 1,
+    strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+);
+
+INSERT INTO a012_wb_sales (
+    id, code, description, document_no, sale_id, sale_date, organization_id, connection_id,
+    supplier_article, nm_id, barcode, product_name, qty, amount_line, total_price,
+    finished_price, event_type, header_json, line_json, state_json, warehouse_json,
+    source_meta_json, is_posted, created_at, updated_at, version
+) VALUES (
+    'e2e00012-0000-4000-8000-000000000012', 'E2E-SALE-1', 'WB sale E2E-ART-1',
+    'e2e-srid-1', 'S-E2E-1', '2026-01-15T10:00:00Z',
+    'e2e00002-0000-4000-8000-000000000002', 'e2e00006-0000-4000-8000-000000000006',
+    'E2E-ART-1', 100000001, '4600000000001', 'E2E товар', 1, 1000, 1000, 900, 'sale',
+    '{"document_no":"e2e-srid-1","sale_id":"S-E2E-1","connection_id":"e2e00006-0000-4000-8000-000000000006","organization_id":"e2e00002-0000-4000-8000-000000000002","marketplace_id":"e2e00005-0000-4000-8000-000000000005"}',
+    '{"line_id":"e2e-line-1","supplier_article":"E2E-ART-1","nm_id":100000001,"barcode":"4600000000001","name":"E2E товар","qty":1.0,"price_list":1500.0,"amount_line":1000.0,"total_price":1000.0,"finished_price":900.0,"payment_sale_amount":900.0}',
+    '{"event_type":"sale","status_norm":"DELIVERED","sale_dt":"2026-01-15T10:00:00Z"}',
+    '{"warehouse_name":"Коледино","warehouse_type":"Склад WB"}',
+    '{"raw_payload_ref":"e2e","fetched_at":"2026-01-15T10:05:00Z","document_version":1}',
+    0, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), 1
+);
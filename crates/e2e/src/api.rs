@@ -0,0 +1,65 @@
+//! HTTP-клиент к тестовому backend с авторизацией (Bearer из `/api/system/auth/login`).
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Value};
+
+#[derive(Clone)]
+pub struct ApiClient {
+    http: reqwest::Client,
+    base_url: String,
+    access_token: String,
+}
+
+impl ApiClient {
+    pub async fn login(base_url: &str, username: &str, password: &str) -> Result<Self> {
+        let http = reqwest::Client::new();
+        let base_url = base_url.trim_end_matches('/').to_string();
+        let response: Value = http
+            .post(format!("{base_url}/api/system/auth/login"))
+            .json(&json!({ "username": username, "password": password }))
+            .send()
+            .await?
+            .error_for_status()
+            .context("login failed")?
+            .json()
+            .await?;
+        let access_token = response["access_token"]
+            .as_str()
+            .ok_or_else(|| anyhow!("login response without access_token"))?
+            .to_string();
+
+        Ok(Self {
+            http,
+            base_url,
+            access_token,
+        })
+    }
+
+    /// POST без тела; ошибка, если статус не 2xx.
+    pub async fn post_empty(&self, path: &str) -> Result<Value> {
+        let response = self
+            .http
+            .post(format!("{}{}", self.base_url, path))
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!("POST {path}: HTTP {status}"));
+        }
+        Ok(response.json().await.unwrap_or(Value::Null))
+    }
+
+    /// Провести продажу WB через тот же endpoint, что и кнопка «Провести».
+    pub async fn post_wb_sale(&self, id: &str) -> Result<()> {
+        self.post_empty(&format!("/api/a012/wb-sales/{id}/post"))
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn unpost_wb_sale(&self, id: &str) -> Result<()> {
+        self.post_empty(&format!("/api/a012/wb-sales/{id}/unpost"))
+            .await
+            .map(|_| ())
+    }
+}
@@ -0,0 +1,130 @@
+//! Запуск backend на временной базе.
+//!
+//! backend читает `config.toml` только из каталога своего бинаря, поэтому бинарь
+//! копируется во временный каталог рядом с тестовым конфигом. Рабочий каталог —
+//! корень workspace: оттуда backend раздаёт `dist/` и находит `migrations/`.
+
+use anyhow::{anyhow, Context, Result};
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+use tokio::process::{Child, Command};
+
+use crate::workspace_root;
+
+/// Порт, на котором backend всегда поднимает сервер.
+const BACKEND_PORT: u16 = 3000;
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(90);
+
+pub struct TestBackend {
+    child: Child,
+    db_path: PathBuf,
+    base_url: String,
+    /// Держит временный каталог (бинарь, конфиг, БД) до конца теста.
+    _dir: TempDir,
+}
+
+impl TestBackend {
+    /// Поднять backend на пустой БД и дождаться `/health`.
+    /// Миграции прогоняет сам backend при старте.
+    pub async fn start() -> Result<Self> {
+        let dir = tempfile::tempdir().context("create temp dir")?;
+        let binary = backend_binary()?;
+        let exe_name = binary
+            .file_name()
+            .ok_or_else(|| anyhow!("invalid backend binary path"))?;
+        let exe_path = dir.path().join(exe_name);
+        std::fs::copy(&binary, &exe_path)
+            .with_context(|| format!("copy {} to temp dir", binary.display()))?;
+
+        let db_path = dir.path().join("e2e.db");
+        write_config(dir.path(), &db_path)?;
+
+        let child = Command::new(&exe_path)
+            .current_dir(workspace_root())
+            .env("RUST_LOG", "warn")
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("spawn {}", exe_path.display()))?;
+
+        let backend = Self {
+            child,
+            db_path,
+            base_url: format!("http://127.0.0.1:{BACKEND_PORT}"),
+            _dir: dir,
+        };
+        backend.wait_ready().await?;
+        Ok(backend)
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Прямое подключение к тестовой БД — для фикстур и проверки проекций.
+    pub async fn db(&self) -> Result<SqlitePool> {
+        let url = format!("sqlite://{}", self.db_path.display());
+        Ok(SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect(&url)
+            .await?)
+    }
+
+    async fn wait_ready(&self) -> Result<()> {
+        let client = reqwest::Client::new();
+        let url = format!("{}/health", self.base_url);
+        let started = Instant::now();
+        loop {
+            if let Ok(response) = client.get(&url).send().await {
+                if response.status().is_success() {
+                    return Ok(());
+                }
+            }
+            if started.elapsed() > STARTUP_TIMEOUT {
+                return Err(anyhow!(
+                    "backend did not answer {} within {:?}",
+                    url,
+                    STARTUP_TIMEOUT
+                ));
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    }
+
+    pub async fn stop(mut self) -> Result<()> {
+        self.child.kill().await?;
+        Ok(())
+    }
+}
+
+fn backend_binary() -> Result<PathBuf> {
+    let path = match std::env::var("E2E_BACKEND_BIN") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => workspace_root()
+            .join("target")
+            .join("debug")
+            .join(format!("backend{}", std::env::consts::EXE_SUFFIX)),
+    };
+    if !path.exists() {
+        return Err(anyhow!(
+            "backend binary not found: {} (cargo build -p backend или E2E_BACKEND_BIN)",
+            path.display()
+        ));
+    }
+    Ok(path)
+}
+
+/// Тестовый конфиг: временная БД, планировщик выключен (фоновые задания не
+/// должны трогать данные сценария), почта и внешние API выключены по умолчанию.
+fn write_config(dir: &Path, db_path: &Path) -> Result<()> {
+    let db_path = db_path.display().to_string().replace('\\', "/");
+    let config =
+        format!("[database]\npath = \"{db_path}\"\n\n[scheduled_tasks]\nenabled = false\n");
+    std::fs::write(dir.join("config.toml"), config).context("write config.toml")?;
+    Ok(())
+}
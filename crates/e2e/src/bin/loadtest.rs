@@ -0,0 +1,54 @@
+//! CLI нагрузочного прогона проведения.
+//!
+//! ```text
+//! cargo run -p e2e --release --bin loadtest -- --docs 2000 --concurrency 4 [--json]
+//! ```
+
+use anyhow::{anyhow, Result};
+use e2e::loadtest::{self, LoadTestConfig};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let mut config = LoadTestConfig {
+        documents: 1000,
+        concurrency: 4,
+    };
+    let mut as_json = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--docs" => config.documents = parse_value(&arg, args.next())?,
+            "--concurrency" => config.concurrency = parse_value(&arg, args.next())?,
+            "--json" => as_json = true,
+            "-h" | "--help" => {
+                println!("loadtest [--docs N] [--concurrency C] [--json]");
+                return Ok(());
+            }
+            other => return Err(anyhow!("unknown argument: {other}")),
+        }
+    }
+
+    let report = loadtest::run(config).await?;
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        report.print();
+    }
+
+    if report.failed > 0 || report.missing_p904 > 0 {
+        return Err(anyhow!(
+            "{} documents failed to post, {} posted without p904 rows",
+            report.failed,
+            report.missing_p904
+        ));
+    }
+    Ok(())
+}
+
+fn parse_value(flag: &str, value: Option<String>) -> Result<usize> {
+    value
+        .ok_or_else(|| anyhow!("{flag} needs a value"))?
+        .parse()
+        .map_err(|e| anyhow!("{flag}: {e}"))
+}
@@ -0,0 +1,117 @@
+//! Headless-браузер через WebDriver и шаги UI, общие для сценариев.
+//!
+//! Локаторы привязаны к видимым подписям и BEM-классам страниц (`login__*`,
+//! `page-action-button__text`), а вкладки открываются через `?active=<tab_key>` —
+//! тот же механизм, которым SPA восстанавливает активную вкладку из URL.
+
+use anyhow::{anyhow, Context, Result};
+use fantoccini::elements::Element;
+use fantoccini::{Client, ClientBuilder, Locator};
+use serde_json::json;
+use std::time::Duration;
+
+const WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub struct Browser {
+    client: Client,
+    base_url: String,
+}
+
+impl Browser {
+    /// Подключиться к WebDriver и открыть headless Chrome.
+    pub async fn connect(base_url: &str) -> Result<Self> {
+        let webdriver_url = std::env::var("E2E_WEBDRIVER_URL")
+            .unwrap_or_else(|_| "http://localhost:9515".to_string());
+
+        let mut capabilities = serde_json::Map::new();
+        capabilities.insert(
+            "goog:chromeOptions".to_string(),
+            json!({ "args": ["--headless=new", "--no-sandbox", "--window-size=1600,1000"] }),
+        );
+
+        let client = ClientBuilder::native()
+            .capabilities(capabilities)
+            .connect(&webdriver_url)
+            .await
+            .with_context(|| format!("connect to WebDriver at {webdriver_url}"))?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Войти в систему через форму логина.
+    pub async fn login(&self, username: &str, password: &str) -> Result<()> {
+        self.client.goto(&self.base_url).await?;
+
+        self.wait_for(Locator::Css(".login__form input[type='text']"))
+            .await?
+            .send_keys(username)
+            .await?;
+        self.wait_for(Locator::Css(".login__form input[type='password']"))
+            .await?
+            .send_keys(password)
+            .await?;
+        self.wait_for(Locator::Css("button.login__button"))
+            .await?
+            .click()
+            .await?;
+
+        self.wait_until_gone(Locator::Css(".login__form"))
+            .await
+            .context("login form still shown after submit")
+    }
+
+    /// Открыть вкладку приложения по ключу (`a012_wb_sales_details_<id>` и т.п.).
+    pub async fn open_tab(&self, tab_key: &str) -> Result<()> {
+        self.client
+            .goto(&format!("{}/?active={}", self.base_url, tab_key))
+            .await?;
+        Ok(())
+    }
+
+    /// Нажать кнопку действия страницы по её подписи («Провести», «Закрыть», ...).
+    pub async fn click_page_action(&self, label: &str) -> Result<()> {
+        let xpath = page_action_xpath(label);
+        self.wait_for(Locator::XPath(&xpath)).await?.click().await?;
+        Ok(())
+    }
+
+    /// Дождаться кнопки действия страницы с подписью `label`.
+    pub async fn wait_page_action(&self, label: &str) -> Result<()> {
+        let xpath = page_action_xpath(label);
+        self.wait_for(Locator::XPath(&xpath)).await.map(|_| ())
+    }
+
+    pub async fn wait_for(&self, locator: Locator<'_>) -> Result<Element> {
+        self.client
+            .wait()
+            .at_most(WAIT_TIMEOUT)
+            .for_element(locator)
+            .await
+            .map_err(|e| anyhow!("element {:?} not found: {e}", locator))
+    }
+
+    async fn wait_until_gone(&self, locator: Locator<'_>) -> Result<()> {
+        let started = std::time::Instant::now();
+        while started.elapsed() < WAIT_TIMEOUT {
+            if self.client.find(locator).await.is_err() {
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        Err(anyhow!("element {:?} still present", locator))
+    }
+
+    pub async fn close(self) -> Result<()> {
+        self.client.close().await?;
+        Ok(())
+    }
+}
+
+fn page_action_xpath(label: &str) -> String {
+    format!(
+        "//button[.//span[contains(@class,'page-action-button__text') and normalize-space()='{label}']]"
+    )
+}
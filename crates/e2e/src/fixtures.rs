@@ -0,0 +1,17 @@
+//! SQL-фикстуры сценариев (`crates/e2e/fixtures/*.sql`).
+
+use anyhow::{Context, Result};
+use sqlx::{Executor, SqlitePool};
+
+/// Продажа WB из `fixtures/wb_sale.sql`.
+pub const WB_SALE_ID: &str = "e2e00012-0000-4000-8000-000000000012";
+
+const WB_SALE_SQL: &str = include_str!("../fixtures/wb_sale.sql");
+
+/// Справочники + одна непроведённая продажа WB.
+pub async fn load_wb_sale(db: &SqlitePool) -> Result<()> {
+    db.execute(WB_SALE_SQL)
+        .await
+        .context("apply fixtures/wb_sale.sql")?;
+    Ok(())
+}
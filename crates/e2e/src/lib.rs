@@ -0,0 +1,27 @@
+//! Сквозной тестовый стенд: backend на временной SQLite + headless-браузер.
+//!
+//! Стенд ничего не собирает сам — он запускает готовые артефакты:
+//! - бинарь backend (`E2E_BACKEND_BIN`, по умолчанию `target/debug/backend`);
+//! - фронт, собранный `trunk build` в `dist/` корня репозитория (backend раздаёт
+//!   его как fallback-сервис относительно рабочего каталога);
+//! - WebDriver (`E2E_WEBDRIVER_URL`, по умолчанию chromedriver на `:9515`).
+//!
+//! Backend слушает фиксированный порт 3000, поэтому сценарии запускаются
+//! последовательно (`--test-threads=1`). Порядок запуска — в `README.md` крейта.
+
+pub mod api;
+pub mod backend;
+pub mod browser;
+pub mod fixtures;
+pub mod loadtest;
+pub mod synthetic;
+
+use std::path::PathBuf;
+
+/// Корень workspace (относительно манифеста этого крейта).
+pub fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .canonicalize()
+        .expect("workspace root must exist")
+}
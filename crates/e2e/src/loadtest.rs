@@ -0,0 +1,147 @@
+//! Нагрузочный прогон проведения: N синтетических продаж WB проводятся через
+//! HTTP API с заданным параллелизмом, замеряются пропускная способность и
+//! задержка «запрос → строки в проекциях».
+//!
+//! Проведение a012 синхронное: ответ `/post` приходит после коммита транзакции
+//! с проекциями, поэтому задержка запроса и есть задержка появления проекций.
+//! После прогона число строк p904 сверяется с числом успешно проведённых документов.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::api::ApiClient;
+use crate::backend::TestBackend;
+use crate::{fixtures, synthetic};
+
+#[derive(Debug, Clone, Copy)]
+pub struct LoadTestConfig {
+    pub documents: usize,
+    pub concurrency: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoadTestReport {
+    pub documents: usize,
+    pub concurrency: usize,
+    pub posted: usize,
+    pub failed: usize,
+    pub generate_ms: u128,
+    pub total_ms: u128,
+    pub docs_per_sec: f64,
+    pub latency_p50_ms: f64,
+    pub latency_p95_ms: f64,
+    pub latency_max_ms: f64,
+    /// Документы, у которых после проведения нет строк в p904.
+    pub missing_p904: i64,
+}
+
+impl LoadTestReport {
+    pub fn print(&self) {
+        println!("documents      {}", self.documents);
+        println!("concurrency    {}", self.concurrency);
+        println!("posted/failed  {}/{}", self.posted, self.failed);
+        println!("generate       {} ms", self.generate_ms);
+        println!("total          {} ms", self.total_ms);
+        println!("throughput     {:.1} docs/s", self.docs_per_sec);
+        println!(
+            "latency        p50 {:.1} ms, p95 {:.1} ms, max {:.1} ms",
+            self.latency_p50_ms, self.latency_p95_ms, self.latency_max_ms
+        );
+        println!("missing p904   {}", self.missing_p904);
+    }
+}
+
+/// Поднять backend на чистой БД, сгенерировать документы и провести их.
+pub async fn run(config: LoadTestConfig) -> Result<LoadTestReport> {
+    let backend = TestBackend::start().await?;
+    let db = backend.db().await?;
+    fixtures::load_wb_sale(&db).await?;
+
+    let generate_started = Instant::now();
+    let ids = synthetic::generate_wb_sales(&db, config.documents).await?;
+    let generate_ms = generate_started.elapsed().as_millis();
+
+    let api = ApiClient::login(backend.base_url(), "admin", "admin").await?;
+    let semaphore = Arc::new(Semaphore::new(config.concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+
+    let started = Instant::now();
+    for id in ids {
+        let api = api.clone();
+        let permit = Arc::clone(&semaphore).acquire_owned().await?;
+        tasks.spawn(async move {
+            let request_started = Instant::now();
+            let result = api.post_wb_sale(&id).await;
+            drop(permit);
+            (result.is_ok(), request_started.elapsed())
+        });
+    }
+
+    let mut latencies = Vec::with_capacity(config.documents);
+    let mut failed = 0;
+    while let Some(joined) = tasks.join_next().await {
+        let (ok, elapsed) = joined?;
+        if ok {
+            latencies.push(elapsed);
+        } else {
+            failed += 1;
+        }
+    }
+    let total = started.elapsed();
+
+    let (missing_p904,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM a012_wb_sales s
+         WHERE s.is_posted = 1
+           AND NOT EXISTS (SELECT 1 FROM p904_sales_data p WHERE p.registrator_ref = s.id)",
+    )
+    .fetch_one(&db)
+    .await?;
+
+    db.close().await;
+    backend.stop().await?;
+
+    latencies.sort();
+    Ok(LoadTestReport {
+        documents: config.documents,
+        concurrency: config.concurrency,
+        posted: latencies.len(),
+        failed,
+        generate_ms,
+        total_ms: total.as_millis(),
+        docs_per_sec: latencies.len() as f64 / total.as_secs_f64().max(f64::EPSILON),
+        latency_p50_ms: percentile_ms(&latencies, 0.50),
+        latency_p95_ms: percentile_ms(&latencies, 0.95),
+        latency_max_ms: latencies.last().map(as_ms).unwrap_or_default(),
+        missing_p904,
+    })
+}
+
+fn as_ms(duration: &Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Перцентиль по отсортированной выборке (метод ближайшего ранга).
+fn percentile_ms(sorted: &[Duration], quantile: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((quantile * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len());
+    as_ms(&sorted[rank - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let sample: Vec<Duration> = (1..=10).map(Duration::from_millis).collect();
+        assert_eq!(percentile_ms(&sample, 0.50), 5.0);
+        assert_eq!(percentile_ms(&sample, 0.95), 10.0);
+        assert_eq!(percentile_ms(&[], 0.95), 0.0);
+    }
+}
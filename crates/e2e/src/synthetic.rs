@@ -0,0 +1,110 @@
+//! Генерация синтетических продаж WB для нагрузочных прогонов.
+//!
+//! Документы ссылаются на справочники из `fixtures/wb_sale.sql` (кабинет,
+//! организация, маркетплейс), поэтому перед генерацией нужно загрузить его.
+//! Данные детерминированы по индексу: прогоны с одинаковым N сравнимы.
+
+use anyhow::Result;
+use serde_json::json;
+use sqlx::SqlitePool;
+
+const CONNECTION_ID: &str = "e2e00006-0000-4000-8000-000000000006";
+const ORGANIZATION_ID: &str = "e2e00002-0000-4000-8000-000000000002";
+const MARKETPLACE_ID: &str = "e2e00005-0000-4000-8000-000000000005";
+
+/// Сколько разных товаров (nm_id) перебирают документы.
+const DISTINCT_PRODUCTS: usize = 200;
+
+/// Вставить `count` непроведённых продаж WB и вернуть их id.
+/// Каждая пятая — возврат; даты раскладываются по 90 дням.
+pub async fn generate_wb_sales(db: &SqlitePool, count: usize) -> Result<Vec<String>> {
+    let mut ids = Vec::with_capacity(count);
+    let mut txn = db.begin().await?;
+
+    for index in 0..count {
+        let id = uuid::Uuid::new_v4().to_string();
+        let srid = format!("load-srid-{index}");
+        let sale_id = format!("S-LOAD-{index}");
+        let nm_id = 200_000_000 + (index % DISTINCT_PRODUCTS) as i64;
+        let article = format!("LOAD-ART-{}", index % DISTINCT_PRODUCTS);
+        let is_return = index % 5 == 4;
+        let event_type = if is_return { "return" } else { "sale" };
+        let sign = if is_return { -1.0 } else { 1.0 };
+        let price = 500.0 + (index % 50) as f64 * 10.0;
+        let sale_dt = format!(
+            "2026-{:02}-{:02}T12:00:00Z",
+            1 + (index / 28) % 3,
+            1 + index % 28
+        );
+
+        let header = json!({
+            "document_no": srid,
+            "sale_id": sale_id,
+            "connection_id": CONNECTION_ID,
+            "organization_id": ORGANIZATION_ID,
+            "marketplace_id": MARKETPLACE_ID,
+        });
+        let line = json!({
+            "line_id": format!("load-line-{index}"),
+            "supplier_article": article,
+            "nm_id": nm_id,
+            "barcode": format!("46{:011}", nm_id),
+            "name": format!("Нагрузочный товар {}", index % DISTINCT_PRODUCTS),
+            "qty": sign,
+            "price_list": price * 1.5,
+            "amount_line": sign * price,
+            "total_price": sign * price,
+            "finished_price": sign * price * 0.9,
+            "payment_sale_amount": sign * price * 0.9,
+        });
+        let state = json!({
+            "event_type": event_type,
+            "status_norm": if is_return { "RETURNED" } else { "DELIVERED" },
+            "sale_dt": sale_dt,
+        });
+        let source_meta = json!({
+            "raw_payload_ref": "loadtest",
+            "fetched_at": sale_dt,
+            "document_version": 1,
+        });
+
+        sqlx::query(
+            "INSERT INTO a012_wb_sales (
+                id, code, description, document_no, sale_id, sale_date, organization_id,
+                connection_id, supplier_article, nm_id, barcode, product_name, qty, amount_line,
+                total_price, finished_price, event_type, header_json, line_json, state_json,
+                warehouse_json, source_meta_json, is_posted, created_at, updated_at, version
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, '{}', ?, 0, ?, ?, 1)",
+        )
+        .bind(&id)
+        .bind(format!("LOAD-{index}"))
+        .bind(format!("WB {event_type} {article}"))
+        .bind(&srid)
+        .bind(&sale_id)
+        .bind(&sale_dt)
+        .bind(ORGANIZATION_ID)
+        .bind(CONNECTION_ID)
+        .bind(&article)
+        .bind(nm_id)
+        .bind(line["barcode"].as_str())
+        .bind(line["name"].as_str())
+        .bind(sign)
+        .bind(sign * price)
+        .bind(sign * price)
+        .bind(sign * price * 0.9)
+        .bind(event_type)
+        .bind(header.to_string())
+        .bind(line.to_string())
+        .bind(state.to_string())
+        .bind(source_meta.to_string())
+        .bind(&sale_dt)
+        .bind(&sale_dt)
+        .execute(&mut *txn)
+        .await?;
+
+        ids.push(id);
+    }
+
+    txn.commit().await?;
+    Ok(ids)
+}
@@ -0,0 +1,51 @@
+//! Сценарий: вход → карточка продажи WB → «Провести» → строки в проекциях.
+//!
+//! Ловит поломки контракта UI ↔ конвейер проведения: если после рефакторинга
+//! posting кнопка перестанет проводить документ или проекции перестанут
+//! заполняться, тест упадёт здесь, а не у пользователя.
+
+use anyhow::Result;
+use e2e::backend::TestBackend;
+use e2e::browser::Browser;
+use e2e::fixtures::{self, WB_SALE_ID};
+
+#[tokio::test]
+#[ignore = "требует собранных backend и dist/, а также запущенного chromedriver — см. crates/e2e/README.md"]
+async fn wb_sale_posting_fills_projections() -> Result<()> {
+    let backend = TestBackend::start().await?;
+    let db = backend.db().await?;
+    fixtures::load_wb_sale(&db).await?;
+
+    let browser = Browser::connect(backend.base_url()).await?;
+    // Пустая БД → backend создаёт пользователя admin/admin при старте.
+    browser.login("admin", "admin").await?;
+
+    browser
+        .open_tab(&format!("a012_wb_sales_details_{WB_SALE_ID}"))
+        .await?;
+    browser.click_page_action("Провести").await?;
+    // После успешного проведения кнопка меняет подпись на «Перепровести».
+    browser.wait_page_action("Перепровести").await?;
+
+    let (is_posted,): (i64,) = sqlx::query_as("SELECT is_posted FROM a012_wb_sales WHERE id = ?")
+        .bind(WB_SALE_ID)
+        .fetch_one(&db)
+        .await?;
+    assert_eq!(is_posted, 1, "a012 must be marked as posted");
+
+    for (table, column) in [
+        ("p900_sales_register", "registrator_ref"),
+        ("p904_sales_data", "registrator_ref"),
+    ] {
+        let (rows,): (i64,) =
+            sqlx::query_as(&format!("SELECT COUNT(*) FROM {table} WHERE {column} = ?"))
+                .bind(WB_SALE_ID)
+                .fetch_one(&db)
+                .await?;
+        assert!(rows > 0, "{table}: no rows for posted WB sale");
+    }
+
+    browser.close().await?;
+    db.close().await;
+    backend.stop().await
+}
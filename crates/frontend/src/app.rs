@@ -1,6 +1,7 @@
 use crate::app_shell::AppShell;
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::change_tokens::ChangeTokenContext;
+use crate::shared::error_toasts::ErrorToastHost;
 use crate::shared::modal_stack::{KeydownGuard, ModalHost, ModalStackService};
 use crate::system::auth::context::AuthProvider;
 use crate::system::tasks::api as tasks_api;
@@ -92,11 +93,15 @@ pub fn App() -> impl IntoView {
     // Provide Thaw theme context for ThemeSelect to update
     provide_context(ThawThemeContext(theme));
 
+    // Брендирование организации для текущего хоста
+    crate::system::branding::context::provide_branding();
+
     view! {
         <ConfigProvider theme>
             <AuthProvider>
                 <AppShell />
                 <ModalHost />
+                <ErrorToastHost />
             </AuthProvider>
         </ConfigProvider>
     }
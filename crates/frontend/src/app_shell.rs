@@ -7,6 +7,7 @@
 use crate::layout::global_context::{AppGlobalContext, Tab as TabData};
 use crate::layout::left::sidebar::Sidebar;
 use crate::layout::right::panel::RightPanel;
+use crate::layout::tab_session;
 use crate::layout::tabs::TabPage;
 use crate::layout::Shell;
 use crate::system::auth::context::use_auth;
@@ -22,6 +23,13 @@ fn MainLayout() -> impl IntoView {
     let tabs_store = leptos::context::use_context::<AppGlobalContext>()
         .expect("AppGlobalContext context not found");
 
+    // Восстановить вкладки прошлой сессии пользователя до разбора ?active=...,
+    // чтобы ссылка из адресной строки открылась поверх них.
+    let (auth_state, _) = use_auth();
+    if let Some(user) = auth_state.get_untracked().user_info {
+        tab_session::restore_and_persist(tabs_store, &user.id);
+    }
+
     // Initialize router integration. This runs once when the component is created.
     tabs_store.init_router_integration();
 
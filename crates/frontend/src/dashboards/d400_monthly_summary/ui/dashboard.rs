@@ -1,4 +1,5 @@
 use crate::dashboards::d400_monthly_summary::api;
+use crate::shared::components::data_freshness_badge::DataFreshnessBadges;
 use chrono::{Datelike, Utc};
 use contracts::dashboards::d400_monthly_summary::MonthlySummaryResponse;
 use js_sys::{Array, Function, Reflect};
@@ -172,6 +173,12 @@ pub fn MonthlySummaryDashboard() -> impl IntoView {
                 }
             }}
 
+            <div class="d400-freshness">
+                <DataFreshnessBadges items=Signal::derive(move || {
+                    data.with(|d| d.as_ref().map(|d| d.freshness.clone()).unwrap_or_default())
+                }) />
+            </div>
+
             <iframe
                 src="assets/dashboards/d400/dashboard.html"
                 style="width: 100%; height: 900px; border: none;"
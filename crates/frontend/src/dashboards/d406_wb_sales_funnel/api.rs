@@ -17,6 +17,8 @@ pub async fn get_wb_sales_funnel(
     connection_mp_ref: &str,
     nm_id: &str,
     axis: FunnelDateAxis,
+    abc_class: &str,
+    xyz_class: &str,
 ) -> Result<WbSalesFunnelResponse, String> {
     let mut params = vec![format!("axis={}", axis_param(axis))];
     if !date_from.trim().is_empty() {
@@ -37,6 +39,12 @@ pub async fn get_wb_sales_funnel(
     if !nm_id.trim().is_empty() {
         params.push(format!("nm_id={}", urlencoding::encode(nm_id.trim())));
     }
+    if !abc_class.is_empty() {
+        params.push(format!("abc_class={}", urlencoding::encode(abc_class)));
+    }
+    if !xyz_class.is_empty() {
+        params.push(format!("xyz_class={}", urlencoding::encode(xyz_class)));
+    }
 
     let url = format!("/api/dashboards/wb-sales-funnel?{}", params.join("&"));
 
@@ -1,5 +1,8 @@
 use crate::dashboards::d406_wb_sales_funnel::api;
 use crate::shared::api_utils::api_base;
+use crate::shared::components::abc_xyz_badge::{
+    abc_filter_options, abc_xyz_badge, xyz_filter_options,
+};
 use crate::shared::export::{export_to_excel, ExcelExportable};
 use crate::shared::page_frame::PageFrame;
 use crate::shared::time_bar_chart::format_number_triads;
@@ -618,6 +621,8 @@ pub fn WbSalesFunnelDashboard() -> impl IntoView {
     let connection_mp_ref = RwSignal::new(String::new());
     let nm_id = RwSignal::new(String::new());
     let axis = RwSignal::new(FunnelDateAxis::Cohort);
+    let abc_class = RwSignal::new(String::new());
+    let xyz_class = RwSignal::new(String::new());
     let data = RwSignal::new(None::<WbSalesFunnelResponse>);
     let loading = RwSignal::new(false);
     let error = RwSignal::new(None::<String>);
@@ -668,10 +673,12 @@ pub fn WbSalesFunnelDashboard() -> impl IntoView {
         let conn = connection_mp_ref.get_untracked();
         let nm = nm_id.get_untracked();
         let ax = axis.get_untracked();
+        let abc = abc_class.get_untracked();
+        let xyz = xyz_class.get_untracked();
         loading.set(true);
         error.set(None);
         spawn_local(async move {
-            match api::get_wb_sales_funnel(&df, &dt, &conn, &nm, ax).await {
+            match api::get_wb_sales_funnel(&df, &dt, &conn, &nm, ax, &abc, &xyz).await {
                 Ok(response) => {
                     data.set(Some(response));
                     loading.set(false);
@@ -848,6 +855,28 @@ pub fn WbSalesFunnelDashboard() -> impl IntoView {
                             <option value="event">"Событие (дата транзакции)"</option>
                         </select>
                     </div>
+                    <div class="d406-field">
+                        <label title="ABC/XYZ-класс товара за последний рассчитанный квартал">"ABC"</label>
+                        <select
+                            prop:value=move || abc_class.get()
+                            on:change=move |ev| abc_class.set(event_target_value(&ev))
+                        >
+                            {abc_filter_options().into_iter().map(|(value, label)| {
+                                view! { <option value=value>{label}</option> }
+                            }).collect_view()}
+                        </select>
+                    </div>
+                    <div class="d406-field">
+                        <label title="CZ — малая доля выручки и нерегулярный спрос">"XYZ"</label>
+                        <select
+                            prop:value=move || xyz_class.get()
+                            on:change=move |ev| xyz_class.set(event_target_value(&ev))
+                        >
+                            {xyz_filter_options().into_iter().map(|(value, label)| {
+                                view! { <option value=value>{label}</option> }
+                            }).collect_view()}
+                        </select>
+                    </div>
                     <div class="d406-actions">
                         <button class="d406-btn d406-btn--primary" on:click=move |_| load() disabled=move || loading.get()>
                             {move || if loading.get() { "Загрузка..." } else { "Обновить" }}
@@ -960,7 +989,9 @@ pub fn WbSalesFunnelDashboard() -> impl IntoView {
                                                     <tr class="d406-row">
                                                         <td class="d406-date">{row.date.clone()}</td>
                                                         <td>{marketplace_badge(&row)}</td>
-                                                        <td class="d406-name" title=name>{article}</td>
+                                                        <td class="d406-name" title=name>
+                                                            {article}" "{abc_xyz_badge(row.abc_xyz.clone())}
+                                                        </td>
                                                         {metric_cells(&dm, &dc, avail, drill)}
                                                     </tr>
                                                 }
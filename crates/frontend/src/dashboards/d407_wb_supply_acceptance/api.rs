@@ -0,0 +1,39 @@
+use contracts::dashboards::d407_wb_supply_acceptance::WbSupplyAcceptanceResponse;
+use gloo_net::http::Request;
+
+pub async fn get_wb_supply_acceptance(
+    date_from: &str,
+    date_to: &str,
+    connection_mp_ref: &str,
+    threshold: i64,
+) -> Result<WbSupplyAcceptanceResponse, String> {
+    let mut params = vec![format!("threshold={threshold}")];
+    if !date_from.trim().is_empty() {
+        params.push(format!(
+            "date_from={}",
+            urlencoding::encode(date_from.trim())
+        ));
+    }
+    if !date_to.trim().is_empty() {
+        params.push(format!("date_to={}", urlencoding::encode(date_to.trim())));
+    }
+    if !connection_mp_ref.trim().is_empty() {
+        params.push(format!(
+            "connection_mp_ref={}",
+            urlencoding::encode(connection_mp_ref.trim())
+        ));
+    }
+
+    let url = format!("/api/dashboards/wb-supply-acceptance?{}", params.join("&"));
+    let response = Request::get(&url)
+        .send()
+        .await
+        .map_err(|error| format!("Request failed: {error}"))?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|error| format!("Failed to parse response: {error}"))
+}
@@ -0,0 +1,338 @@
+use crate::dashboards::d407_wb_supply_acceptance::api;
+use crate::layout::global_context::AppGlobalContext;
+use crate::shared::api_utils::api_base;
+use crate::shared::export::{export_to_excel, ExcelExportable};
+use crate::shared::icons::icon;
+use crate::shared::page_frame::PageFrame;
+use chrono::{Datelike, Months, Utc};
+use contracts::dashboards::d407_wb_supply_acceptance::{
+    WbSupplyAcceptanceResponse, WbSupplyAcceptanceRow, DEFAULT_SHORTFALL_THRESHOLD,
+};
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::common::AggregateId;
+use gloo_net::http::Request;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+impl ExcelExportable for WbSupplyAcceptanceRow {
+    fn headers() -> Vec<&'static str> {
+        vec![
+            "Поставка",
+            "Название",
+            "Создана",
+            "nmId",
+            "Артикул",
+            "Отгружено",
+            "Принято WB",
+            "Продано",
+            "Недостача",
+        ]
+    }
+
+    fn to_csv_row(&self) -> Vec<String> {
+        vec![
+            self.supply_id.clone(),
+            self.supply_name.clone().unwrap_or_default(),
+            date_only(self.created_at_wb.as_deref()),
+            self.nm_id.map(|v| v.to_string()).unwrap_or_default(),
+            self.article.clone(),
+            self.shipped_qty.to_string(),
+            self.accepted_qty.to_string(),
+            self.sold_qty.to_string(),
+            self.shortfall.to_string(),
+        ]
+    }
+}
+
+fn date_only(value: Option<&str>) -> String {
+    value
+        .map(|v| v.chars().take(10).collect())
+        .unwrap_or_default()
+}
+
+/// Период по умолчанию — прошлый и текущий месяц: поставка принимается WB не сразу.
+fn default_date_from() -> String {
+    let today = Utc::now().date_naive();
+    let first = today.with_day(1).unwrap_or(today);
+    first
+        .checked_sub_months(Months::new(1))
+        .unwrap_or(first)
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn today() -> String {
+    Utc::now().date_naive().format("%Y-%m-%d").to_string()
+}
+
+#[component]
+pub fn WbSupplyAcceptanceDashboard() -> impl IntoView {
+    let tabs = expect_context::<AppGlobalContext>();
+    let date_from = RwSignal::new(default_date_from());
+    let date_to = RwSignal::new(today());
+    let connection_mp_ref = RwSignal::new(String::new());
+    let threshold = RwSignal::new(DEFAULT_SHORTFALL_THRESHOLD);
+    let only_flagged = RwSignal::new(true);
+    let cabinets = RwSignal::new(Vec::<(String, String)>::new());
+    let data = RwSignal::new(None::<WbSupplyAcceptanceResponse>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    spawn_local(async move {
+        let url = format!("{}/api/connection_mp", api_base());
+        let Ok(resp) = Request::get(&url).send().await else {
+            return;
+        };
+        if !resp.ok() {
+            return;
+        }
+        if let Ok(data) = resp.json::<Vec<ConnectionMP>>().await {
+            let mut opts: Vec<(String, String)> = data
+                .into_iter()
+                .map(|conn| {
+                    let label = if conn.base.description.trim().is_empty() {
+                        conn.base.code.clone()
+                    } else {
+                        conn.base.description.clone()
+                    };
+                    (conn.base.id.as_string(), label)
+                })
+                .collect();
+            opts.sort_by(|a, b| a.1.cmp(&b.1));
+            cabinets.set(opts);
+        }
+    });
+
+    let load = move || {
+        let df = date_from.get_untracked();
+        let dt = date_to.get_untracked();
+        let conn = connection_mp_ref.get_untracked();
+        let th = threshold.get_untracked();
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::get_wb_supply_acceptance(&df, &dt, &conn, th).await {
+                Ok(response) => data.set(Some(response)),
+                Err(message) => error.set(Some(message)),
+            }
+            loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| load());
+
+    let visible_rows = move || {
+        data.with(|response| {
+            response
+                .as_ref()
+                .map(|r| {
+                    r.rows
+                        .iter()
+                        .filter(|row| !only_flagged.get() || row.is_flagged)
+                        .cloned()
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        })
+    };
+
+    let export_claims = move |_| {
+        let flagged = data.with_untracked(|response| {
+            response
+                .as_ref()
+                .map(|r| {
+                    r.rows
+                        .iter()
+                        .filter(|row| row.is_flagged)
+                        .cloned()
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        });
+        let filename = format!(
+            "wb_supply_shortfall_{}_{}.csv",
+            date_from.get_untracked(),
+            date_to.get_untracked()
+        );
+        if let Err(message) = export_to_excel(&flagged, &filename) {
+            error.set(Some(message));
+        }
+    };
+
+    view! {
+        <PageFrame page_id="d407_wb_supply_acceptance--dashboard" category="dashboard" class="page--wide">
+            <style>
+                ".d407-shell{display:flex;flex-direction:column;gap:12px;height:100%}
+                .d407-toolbar{display:flex;gap:10px;align-items:end;flex-wrap:wrap;padding:10px 0}
+                .d407-field{display:flex;flex-direction:column;gap:4px;min-width:140px}
+                .d407-field label{font-size:12px;color:var(--color-text-secondary)}
+                .d407-field input,.d407-field select{height:32px;border:1px solid var(--color-border);border-radius:6px;padding:0 8px;background:var(--color-surface);color:var(--color-text-primary)}
+                .d407-check{display:flex;gap:6px;align-items:center;height:32px;font-size:13px}
+                .d407-btn{height:32px;border:1px solid var(--color-border);border-radius:6px;background:var(--color-surface);color:var(--color-text-primary);padding:0 12px;cursor:pointer;display:inline-flex;gap:6px;align-items:center}
+                .d407-summary{display:flex;gap:18px;flex-wrap:wrap;border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;padding:10px;background:var(--color-surface)}
+                .d407-summary span{font-size:12px;color:var(--color-text-secondary)}
+                .d407-summary strong{font-size:14px;color:var(--color-text-primary);font-variant-numeric:tabular-nums}
+                .d407-table-wrap{overflow:auto;border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface)}
+                .d407-table{width:100%;border-collapse:collapse;font-size:13px}
+                .d407-table th{position:sticky;top:0;background:var(--color-surface);z-index:1;text-align:left;border-bottom:1px solid var(--color-border);padding:8px;color:var(--color-text-secondary);font-weight:600}
+                .d407-table td{border-bottom:1px solid var(--color-border-light,var(--color-border));padding:7px 8px}
+                .d407-num{text-align:right;font-variant-numeric:tabular-nums}
+                .d407-row--flagged{background:color-mix(in srgb,#dc2626 7%,transparent)}
+                .d407-shortfall{color:#dc2626;font-weight:600}
+                .d407-link{border:0;background:transparent;color:var(--color-brand,#2563eb);cursor:pointer;font:inherit;padding:0}
+                .d407-link:hover{text-decoration:underline}
+                .d407-state{padding:18px;color:var(--color-text-secondary)}"
+            </style>
+            <div class="d407-shell">
+                <div>
+                    <h1 style="margin:0;font-size:20px;">"Приёмка поставок WB"</h1>
+                    <div style="color:var(--color-text-secondary);font-size:13px;">
+                        "Отгружено по поставке против принятого и проданного по данным WB (income_id заказов)"
+                    </div>
+                </div>
+
+                <div class="d407-toolbar">
+                    <div class="d407-field">
+                        <label>"Поставки с"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_from.get()
+                            on:input=move |ev| date_from.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d407-field">
+                        <label>"по"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_to.get()
+                            on:input=move |ev| date_to.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d407-field">
+                        <label>"Кабинет"</label>
+                        <select
+                            prop:value=move || connection_mp_ref.get()
+                            on:change=move |ev| connection_mp_ref.set(event_target_value(&ev))
+                        >
+                            <option value="">"Все кабинеты"</option>
+                            <For
+                                each=move || cabinets.get()
+                                key=|(id, _)| id.clone()
+                                children=move |(id, label)| {
+                                    view! { <option value=id.clone()>{label}</option> }
+                                }
+                            />
+                        </select>
+                    </div>
+                    <div class="d407-field">
+                        <label>"Порог недостачи, шт."</label>
+                        <input
+                            type="number"
+                            min="0"
+                            prop:value=move || threshold.get().to_string()
+                            on:input=move |ev| {
+                                threshold.set(event_target_value(&ev).parse::<i64>().unwrap_or(0).max(0))
+                            }
+                        />
+                    </div>
+                    <label class="d407-check">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || only_flagged.get()
+                            on:change=move |ev| only_flagged.set(event_target_checked(&ev))
+                        />
+                        "Только недостачи"
+                    </label>
+                    <button class="d407-btn" on:click=move |_| load() disabled=move || loading.get()>
+                        {move || if loading.get() { "Загрузка..." } else { "Обновить" }}
+                    </button>
+                    <button
+                        class="d407-btn"
+                        on:click=export_claims
+                        disabled=move || !data.with(|r| matches!(r, Some(r) if r.totals.flagged_rows > 0))
+                    >
+                        {icon("download")}
+                        "Претензия (CSV)"
+                    </button>
+                </div>
+
+                {move || error.get().map(|message| view! {
+                    <div class="d407-state">{message}</div>
+                })}
+
+                {move || data.get().map(|response| {
+                    let totals = response.totals.clone();
+                    view! {
+                        <div class="d407-summary">
+                            <span>"Поставок: " <strong>{totals.supplies}</strong></span>
+                            <span>"Отгружено: " <strong>{totals.shipped_qty}</strong></span>
+                            <span>"Принято WB: " <strong>{totals.accepted_qty}</strong></span>
+                            <span>"Продано: " <strong>{totals.sold_qty}</strong></span>
+                            <span>"Недостача: " <strong>{totals.shortfall}</strong></span>
+                            <span>"Строк к претензии: " <strong>{totals.flagged_rows}</strong></span>
+                        </div>
+                    }
+                })}
+
+                <div class="d407-table-wrap">
+                    <table class="d407-table">
+                        <thead>
+                            <tr>
+                                <th>"Поставка"</th>
+                                <th>"Создана"</th>
+                                <th>"nmId"</th>
+                                <th>"Артикул"</th>
+                                <th class="d407-num">"Отгружено"</th>
+                                <th class="d407-num">"Принято WB"</th>
+                                <th class="d407-num">"Продано"</th>
+                                <th class="d407-num">"Недостача"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {move || {
+                                let rows = visible_rows();
+                                if rows.is_empty() {
+                                    let message = if only_flagged.get() {
+                                        "Нет недостач за выбранный период."
+                                    } else {
+                                        "Нет поставок за выбранный период."
+                                    };
+                                    return view! {
+                                        <tr><td class="d407-state" colspan="8">{message}</td></tr>
+                                    }.into_any();
+                                }
+                                rows.into_iter().map(|row| {
+                                    let tab_key = format!("a029_wb_supply_details_{}", row.supply_ref);
+                                    let tab_title = format!("Поставка {}", row.supply_id);
+                                    let tabs_for_click = tabs.clone();
+                                    view! {
+                                        <tr class:d407-row--flagged=row.is_flagged>
+                                            <td>
+                                                <button
+                                                    class="d407-link"
+                                                    title=row.supply_name.clone().unwrap_or_default()
+                                                    on:click=move |_| tabs_for_click.open_tab(&tab_key, &tab_title)
+                                                >
+                                                    {row.supply_id.clone()}
+                                                </button>
+                                            </td>
+                                            <td>{date_only(row.created_at_wb.as_deref())}</td>
+                                            <td>{row.nm_id.map(|v| v.to_string()).unwrap_or_default()}</td>
+                                            <td>{row.article.clone()}</td>
+                                            <td class="d407-num">{row.shipped_qty}</td>
+                                            <td class="d407-num">{row.accepted_qty}</td>
+                                            <td class="d407-num">{row.sold_qty}</td>
+                                            <td class="d407-num" class:d407-shortfall={row.shortfall > 0}>
+                                                {row.shortfall}
+                                            </td>
+                                        </tr>
+                                    }
+                                }).collect_view().into_any()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+            </div>
+        </PageFrame>
+    }
+}
@@ -0,0 +1,36 @@
+use contracts::dashboards::d408_pnl_statement::PnlStatementResponse;
+use gloo_net::http::Request;
+
+pub async fn get_pnl_statement(
+    date_from: &str,
+    date_to: &str,
+    organization_ref: &str,
+    layer: &str,
+) -> Result<PnlStatementResponse, String> {
+    let mut params = vec![
+        format!("date_from={}", urlencoding::encode(date_from.trim())),
+        format!("date_to={}", urlencoding::encode(date_to.trim())),
+    ];
+    if !organization_ref.trim().is_empty() {
+        params.push(format!(
+            "organization_ref={}",
+            urlencoding::encode(organization_ref.trim())
+        ));
+    }
+    if !layer.trim().is_empty() {
+        params.push(format!("layer={}", urlencoding::encode(layer.trim())));
+    }
+
+    let url = format!("/api/dashboards/pnl-statement?{}", params.join("&"));
+    let response = Request::get(&url)
+        .send()
+        .await
+        .map_err(|error| format!("Request failed: {error}"))?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|error| format!("Failed to parse response: {error}"))
+}
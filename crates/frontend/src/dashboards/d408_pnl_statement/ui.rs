@@ -0,0 +1,389 @@
+use crate::dashboards::d408_pnl_statement::api;
+use crate::general_ledger::api::create_gl_drilldown_session;
+use crate::layout::global_context::AppGlobalContext;
+use crate::shared::api_utils::api_base;
+use crate::shared::export::{export_rows_to_xlsx, XlsxCell};
+use crate::shared::icons::icon;
+use crate::shared::money_format::format_money;
+use crate::shared::page_frame::PageFrame;
+use chrono::{Datelike, Utc};
+use contracts::dashboards::d408_pnl_statement::{
+    month_bounds, PnlOrganizationSection, PnlStatementResponse, PnlTurnoverRow, DRILLDOWN_GROUP_BY,
+};
+use contracts::domain::a002_organization::aggregate::Organization;
+use contracts::domain::common::AggregateId;
+use contracts::general_ledger::{GlDrilldownQuery, GlDrilldownSessionCreate, GL_LAYER_CLASSES};
+use gloo_net::http::Request;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::collections::HashSet;
+
+/// Период по умолчанию — с начала года по сегодня.
+fn default_date_from() -> String {
+    let today = Utc::now().date_naive();
+    format!("{:04}-01-01", today.year())
+}
+
+fn today() -> String {
+    Utc::now().date_naive().format("%Y-%m-%d").to_string()
+}
+
+fn section_rows(section: &PnlOrganizationSection) -> Vec<Vec<XlsxCell>> {
+    let numbers = |values: &[f64], total: f64| {
+        values
+            .iter()
+            .chain(std::iter::once(&total))
+            .map(|value| XlsxCell::Number(*value))
+            .collect::<Vec<_>>()
+    };
+    let mut rows = Vec::new();
+    for line in &section.lines {
+        let mut row = vec![
+            XlsxCell::Text(section.organization_name.clone()),
+            XlsxCell::Text(line.label.clone()),
+            XlsxCell::Text(String::new()),
+        ];
+        row.extend(numbers(&line.values, line.total));
+        rows.push(row);
+        for turnover in &line.turnovers {
+            let mut row = vec![
+                XlsxCell::Text(section.organization_name.clone()),
+                XlsxCell::Text(line.label.clone()),
+                XlsxCell::Text(format!("{} ({})", turnover.turnover_name, turnover.layer)),
+            ];
+            row.extend(numbers(&turnover.values, turnover.total));
+            rows.push(row);
+        }
+    }
+    let mut row = vec![
+        XlsxCell::Text(section.organization_name.clone()),
+        XlsxCell::Text("Финансовый результат".to_string()),
+        XlsxCell::Text(String::new()),
+    ];
+    row.extend(numbers(&section.result, section.result_total));
+    rows.push(row);
+    rows
+}
+
+fn export_xlsx(response: &PnlStatementResponse) -> Result<(), String> {
+    let mut header = vec![
+        XlsxCell::Text("Организация".to_string()),
+        XlsxCell::Text("Статья".to_string()),
+        XlsxCell::Text("Оборот".to_string()),
+    ];
+    header.extend(response.months.iter().cloned().map(XlsxCell::Text));
+    header.push(XlsxCell::Text("Итого".to_string()));
+
+    let mut rows = vec![header];
+    for section in &response.organizations {
+        rows.extend(section_rows(section));
+    }
+    let filename = format!(
+        "pnl_{}_{}.xlsx",
+        response.filters.date_from, response.filters.date_to
+    );
+    export_rows_to_xlsx(&rows, "P&L", &filename)
+}
+
+#[component]
+pub fn PnlStatementDashboard() -> impl IntoView {
+    let tabs = expect_context::<AppGlobalContext>();
+    let date_from = RwSignal::new(default_date_from());
+    let date_to = RwSignal::new(today());
+    let organization_ref = RwSignal::new(String::new());
+    let layer = RwSignal::new(String::new());
+    let organizations = RwSignal::new(Vec::<(String, String)>::new());
+    let data = RwSignal::new(None::<PnlStatementResponse>);
+    let expanded = RwSignal::new(HashSet::<String>::new());
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    spawn_local(async move {
+        let url = format!("{}/api/organization", api_base());
+        let Ok(resp) = Request::get(&url).send().await else {
+            return;
+        };
+        if !resp.ok() {
+            return;
+        }
+        if let Ok(data) = resp.json::<Vec<Organization>>().await {
+            let mut opts: Vec<(String, String)> = data
+                .into_iter()
+                .map(|org| (org.base.id.as_string(), org.base.description))
+                .collect();
+            opts.sort_by(|a, b| a.1.cmp(&b.1));
+            organizations.set(opts);
+        }
+    });
+
+    let load = move || {
+        let df = date_from.get_untracked();
+        let dt = date_to.get_untracked();
+        let org = organization_ref.get_untracked();
+        let lay = layer.get_untracked();
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::get_pnl_statement(&df, &dt, &org, &lay).await {
+                Ok(response) => data.set(Some(response)),
+                Err(message) => error.set(Some(message)),
+            }
+            loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| load());
+
+    let toggle = move |key: String| {
+        expanded.update(|set| {
+            if !set.remove(&key) {
+                set.insert(key);
+            }
+        });
+    };
+
+    // Ячейка оборота → GL-детализация по документам-регистраторам за месяц.
+    let open_drilldown = move |section: PnlOrganizationSection,
+                               turnover: PnlTurnoverRow,
+                               month: Option<String>| {
+        let Some(filters) = data.with_untracked(|r| r.as_ref().map(|r| r.filters.clone())) else {
+            return;
+        };
+        let (df, dt) = match month.as_deref() {
+            Some(month) => month_bounds(month, &filters.date_from, &filters.date_to),
+            None => (filters.date_from, filters.date_to),
+        };
+        let tab_title = format!(
+            "{} / {} / {}",
+            section.organization_name,
+            turnover.turnover_name,
+            month.unwrap_or_else(|| "период".to_string())
+        );
+        let body = GlDrilldownSessionCreate {
+            title: Some(tab_title.clone()),
+            query: GlDrilldownQuery {
+                turnover_code: turnover.turnover_code,
+                group_by: DRILLDOWN_GROUP_BY.to_string(),
+                date_from: df,
+                date_to: dt,
+                connection_mp_ref: None,
+                connection_mp_refs: section.connection_mp_refs,
+                account: None,
+                layer: Some(turnover.layer),
+                entity: None,
+                corr_account: None,
+            },
+        };
+        spawn_local(async move {
+            match create_gl_drilldown_session(&body).await {
+                Ok(session) => {
+                    let tab_key = format!("gl_drilldown__{}", session.session_id);
+                    tabs.open_tab(&tab_key, &tab_title);
+                }
+                Err(message) => error.set(Some(message)),
+            }
+        });
+    };
+
+    let on_export = move |_| {
+        let result = data.with_untracked(|response| match response {
+            Some(response) => export_xlsx(response),
+            None => Err("Нет данных для экспорта".to_string()),
+        });
+        if let Err(message) = result {
+            error.set(Some(message));
+        }
+    };
+
+    view! {
+        <PageFrame page_id="d408_pnl_statement--dashboard" category="dashboard" class="page--wide">
+            <style>
+                ".d408-shell{display:flex;flex-direction:column;gap:12px;height:100%}
+                .d408-toolbar{display:flex;gap:10px;align-items:end;flex-wrap:wrap;padding:10px 0}
+                .d408-field{display:flex;flex-direction:column;gap:4px;min-width:140px}
+                .d408-field label{font-size:12px;color:var(--color-text-secondary)}
+                .d408-field input,.d408-field select{height:32px;border:1px solid var(--color-border);border-radius:6px;padding:0 8px;background:var(--color-surface);color:var(--color-text-primary)}
+                .d408-btn{height:32px;border:1px solid var(--color-border);border-radius:6px;background:var(--color-surface);color:var(--color-text-primary);padding:0 12px;cursor:pointer;display:inline-flex;gap:6px;align-items:center}
+                .d408-table-wrap{overflow:auto;border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface)}
+                .d408-table{width:100%;border-collapse:collapse;font-size:13px}
+                .d408-table th{position:sticky;top:0;background:var(--color-surface);z-index:1;text-align:left;border-bottom:1px solid var(--color-border);padding:8px;color:var(--color-text-secondary);font-weight:600;white-space:nowrap}
+                .d408-table td{border-bottom:1px solid var(--color-border-light,var(--color-border));padding:6px 8px}
+                .d408-num{text-align:right;font-variant-numeric:tabular-nums;white-space:nowrap}
+                .d408-neg{color:#dc2626}
+                .d408-org td{font-weight:700;background:color-mix(in srgb,var(--color-brand,#2563eb) 6%,transparent)}
+                .d408-line td:first-child{cursor:pointer;font-weight:600}
+                .d408-turnover td:first-child{padding-left:28px;color:var(--color-text-secondary)}
+                .d408-result td{font-weight:700;border-bottom:2px solid var(--color-border)}
+                .d408-link{border:0;background:transparent;color:inherit;cursor:pointer;font:inherit;padding:0}
+                .d408-link:hover{text-decoration:underline;color:var(--color-brand,#2563eb)}
+                .d408-state{padding:18px;color:var(--color-text-secondary)}"
+            </style>
+            <div class="d408-shell">
+                <div>
+                    <h1 style="margin:0;font-size:20px;">"P&L по организациям"</h1>
+                    <div style="color:var(--color-text-secondary);font-size:13px;">
+                        "Помесячный финансовый результат из проводок GL: доходы со знаком плюс, расходы — минус. Клик по сумме оборота открывает детализацию по документам."
+                    </div>
+                </div>
+
+                <div class="d408-toolbar">
+                    <div class="d408-field">
+                        <label>"Период с"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_from.get()
+                            on:input=move |ev| date_from.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d408-field">
+                        <label>"по"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_to.get()
+                            on:input=move |ev| date_to.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d408-field">
+                        <label>"Организация"</label>
+                        <select
+                            prop:value=move || organization_ref.get()
+                            on:change=move |ev| organization_ref.set(event_target_value(&ev))
+                        >
+                            <option value="">"Все организации"</option>
+                            <For
+                                each=move || organizations.get()
+                                key=|(id, _)| id.clone()
+                                children=move |(id, label)| {
+                                    view! { <option value=id.clone()>{label}</option> }
+                                }
+                            />
+                        </select>
+                    </div>
+                    <div class="d408-field">
+                        <label>"Слой GL"</label>
+                        <select
+                            prop:value=move || layer.get()
+                            on:change=move |ev| layer.set(event_target_value(&ev))
+                        >
+                            <option value="">"По статьям (fina / oper / prod)"</option>
+                            {GL_LAYER_CLASSES
+                                .iter()
+                                .map(|def| view! { <option value=def.code>{def.name}</option> })
+                                .collect_view()}
+                        </select>
+                    </div>
+                    <button class="d408-btn" on:click=move |_| load() disabled=move || loading.get()>
+                        {move || if loading.get() { "Загрузка..." } else { "Обновить" }}
+                    </button>
+                    <button
+                        class="d408-btn"
+                        on:click=on_export
+                        disabled=move || !data.with(|r| matches!(r, Some(r) if !r.organizations.is_empty()))
+                    >
+                        {icon("download")}
+                        "Excel (XLSX)"
+                    </button>
+                </div>
+
+                {move || error.get().map(|message| view! {
+                    <div class="d408-state">{message}</div>
+                })}
+
+                {move || data.get().map(|response| {
+                    let months = response.months.clone();
+                    let colspan = (months.len() + 2).to_string();
+                    let amount_cell = |value: f64| {
+                        view! {
+                            <td class="d408-num" class:d408-neg={value < 0.0}>{format_money(value)}</td>
+                        }
+                    };
+                    let body = if response.organizations.is_empty() {
+                        view! {
+                            <tr><td class="d408-state" colspan=colspan.clone()>"Нет проводок за выбранный период."</td></tr>
+                        }.into_any()
+                    } else {
+                        response.organizations.into_iter().map(|section| {
+                            let lines = section.lines.clone();
+                            let result = section.result.clone();
+                            let result_total = section.result_total;
+                            let org_name = section.organization_name.clone();
+                            let line_views = lines.into_iter().map(|line| {
+                                let key = format!("{}|{:?}", section.organization_ref, line.line);
+                                let key_for_toggle = key.clone();
+                                let is_open = Signal::derive(move || expanded.with(|set| set.contains(&key)));
+                                let months_for_turnovers = months.clone();
+                                let section_for_turnovers = section.clone();
+                                let turnovers = line.turnovers.clone();
+                                view! {
+                                    <tr class="d408-line">
+                                        <td on:click=move |_| toggle(key_for_toggle.clone())>
+                                            {move || if is_open.get() { "▾ " } else { "▸ " }}
+                                            {line.label.clone()}
+                                        </td>
+                                        {line.values.iter().map(|value| amount_cell(*value)).collect_view()}
+                                        {amount_cell(line.total)}
+                                    </tr>
+                                    <Show when=move || is_open.get()>
+                                        {turnovers.iter().map(|turnover| {
+                                            let cells = months_for_turnovers
+                                                .iter()
+                                                .cloned()
+                                                .map(Some)
+                                                .zip(turnover.values.iter().copied())
+                                                .chain(std::iter::once((None, turnover.total)))
+                                                .map(|(month, value)| {
+                                                    let section = section_for_turnovers.clone();
+                                                    let turnover = turnover.clone();
+                                                    view! {
+                                                        <td class="d408-num" class:d408-neg={value < 0.0}>
+                                                            <button
+                                                                class="d408-link"
+                                                                title="Детализация по документам"
+                                                                on:click=move |_| open_drilldown(section.clone(), turnover.clone(), month.clone())
+                                                            >
+                                                                {format_money(value)}
+                                                            </button>
+                                                        </td>
+                                                    }
+                                                })
+                                                .collect_view();
+                                            view! {
+                                                <tr class="d408-turnover">
+                                                    <td>{format!("{} ({})", turnover.turnover_name, turnover.layer)}</td>
+                                                    {cells}
+                                                </tr>
+                                            }
+                                        }).collect_view()}
+                                    </Show>
+                                }
+                            }).collect_view();
+                            view! {
+                                <tr class="d408-org"><td colspan=colspan.clone()>{org_name}</td></tr>
+                                {line_views}
+                                <tr class="d408-result">
+                                    <td>"Финансовый результат"</td>
+                                    {result.iter().map(|value| amount_cell(*value)).collect_view()}
+                                    {amount_cell(result_total)}
+                                </tr>
+                            }
+                        }).collect_view().into_any()
+                    };
+                    view! {
+                        <div class="d408-table-wrap">
+                            <table class="d408-table">
+                                <thead>
+                                    <tr>
+                                        <th>"Статья"</th>
+                                        {months.iter().map(|month| view! { <th class="d408-num">{month.clone()}</th> }).collect_view()}
+                                        <th class="d408-num">"Итого"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>{body}</tbody>
+                            </table>
+                        </div>
+                    }
+                })}
+            </div>
+        </PageFrame>
+    }
+}
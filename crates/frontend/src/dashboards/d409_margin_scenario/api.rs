@@ -0,0 +1,20 @@
+use contracts::dashboards::d409_margin_scenario::{MarginScenarioRequest, MarginScenarioResponse};
+use gloo_net::http::Request;
+
+pub async fn calculate_margin_scenario(
+    request: &MarginScenarioRequest,
+) -> Result<MarginScenarioResponse, String> {
+    let response = Request::post("/api/dashboards/margin-scenario")
+        .json(request)
+        .map_err(|error| format!("Failed to serialize request: {error}"))?
+        .send()
+        .await
+        .map_err(|error| format!("Request failed: {error}"))?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|error| format!("Failed to parse response: {error}"))
+}
@@ -0,0 +1,363 @@
+use crate::dashboards::d409_margin_scenario::api;
+use crate::shared::api_utils::api_base;
+use crate::shared::components::abc_xyz_badge::abc_xyz_badge;
+use crate::shared::money_format::{
+    format_money, format_number, format_percent, format_percent_opt,
+};
+use crate::shared::page_frame::PageFrame;
+use chrono::{Duration, Utc};
+use contracts::dashboards::d409_margin_scenario::{
+    MarginAssumptions, MarginScenarioItem, MarginScenarioRequest, MarginScenarioResponse,
+    MAX_SCENARIO_ITEMS,
+};
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::common::AggregateId;
+use contracts::system::bulk_ops::parse_id_list;
+use gloo_net::http::Request;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::collections::HashMap;
+
+/// Факт по умолчанию — последние 30 дней.
+fn default_date_from() -> String {
+    (Utc::now().date_naive() - Duration::days(30))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn today() -> String {
+    Utc::now().date_naive().format("%Y-%m-%d").to_string()
+}
+
+/// Пустое поле допущения — «как в факте».
+fn parse_assumption(value: &str) -> Option<f64> {
+    value.trim().replace(',', ".").parse::<f64>().ok()
+}
+
+#[component]
+fn AssumptionField(label: &'static str, value: RwSignal<String>) -> impl IntoView {
+    view! {
+        <div class="d409-field">
+            <label>{label}</label>
+            <input
+                type="text"
+                inputmode="decimal"
+                placeholder="факт"
+                prop:value=move || value.get()
+                on:input=move |ev| value.set(event_target_value(&ev))
+            />
+        </div>
+    }
+}
+
+#[component]
+pub fn MarginScenarioDashboard() -> impl IntoView {
+    let date_from = RwSignal::new(default_date_from());
+    let date_to = RwSignal::new(today());
+    let connection_mp_ref = RwSignal::new(String::new());
+    let nm_ids_text = RwSignal::new(String::new());
+    let price_change = RwSignal::new(String::new());
+    let commission = RwSignal::new(String::new());
+    let acquiring = RwSignal::new(String::new());
+    let logistics = RwSignal::new(String::new());
+    let cost_change = RwSignal::new(String::new());
+    let price_overrides = RwSignal::new(HashMap::<i64, f64>::new());
+    let cabinets = RwSignal::new(Vec::<(String, String)>::new());
+    let data = RwSignal::new(None::<MarginScenarioResponse>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    spawn_local(async move {
+        let url = format!("{}/api/connection_mp", api_base());
+        let Ok(resp) = Request::get(&url).send().await else {
+            return;
+        };
+        if !resp.ok() {
+            return;
+        }
+        if let Ok(data) = resp.json::<Vec<ConnectionMP>>().await {
+            let mut opts: Vec<(String, String)> = data
+                .into_iter()
+                .map(|conn| {
+                    let label = if conn.base.description.trim().is_empty() {
+                        conn.base.code.clone()
+                    } else {
+                        conn.base.description.clone()
+                    };
+                    (conn.base.id.as_string(), label)
+                })
+                .collect();
+            opts.sort_by(|a, b| a.1.cmp(&b.1));
+            cabinets.set(opts);
+        }
+    });
+
+    let calculate = move || {
+        let nm_ids: Vec<i64> = parse_id_list(&nm_ids_text.get_untracked())
+            .into_iter()
+            .filter_map(|id| id.parse::<i64>().ok())
+            .collect();
+        if nm_ids.is_empty() {
+            error.set(Some("Укажите хотя бы один nmId.".to_string()));
+            return;
+        }
+        if nm_ids.len() > MAX_SCENARIO_ITEMS {
+            error.set(Some(format!(
+                "Не больше {MAX_SCENARIO_ITEMS} SKU в одном сценарии."
+            )));
+            return;
+        }
+        let overrides = price_overrides.get_untracked();
+        let connection = connection_mp_ref.get_untracked();
+        let request = MarginScenarioRequest {
+            date_from: date_from.get_untracked(),
+            date_to: date_to.get_untracked(),
+            connection_mp_ref: (!connection.is_empty()).then_some(connection),
+            items: nm_ids
+                .into_iter()
+                .map(|nm_id| MarginScenarioItem {
+                    nm_id,
+                    price: overrides.get(&nm_id).copied(),
+                })
+                .collect(),
+            assumptions: MarginAssumptions {
+                price_change_percent: parse_assumption(&price_change.get_untracked()),
+                commission_percent: parse_assumption(&commission.get_untracked()),
+                acquiring_percent: parse_assumption(&acquiring.get_untracked()),
+                logistics_per_unit: parse_assumption(&logistics.get_untracked()),
+                cost_change_percent: parse_assumption(&cost_change.get_untracked()),
+            },
+        };
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::calculate_margin_scenario(&request).await {
+                Ok(response) => data.set(Some(response)),
+                Err(message) => error.set(Some(message)),
+            }
+            loading.set(false);
+        });
+    };
+
+    let set_price_override = move |nm_id: i64, value: String| {
+        price_overrides.update(|map| match parse_assumption(&value) {
+            Some(price) if price > 0.0 => {
+                map.insert(nm_id, price);
+            }
+            _ => {
+                map.remove(&nm_id);
+            }
+        });
+        calculate();
+    };
+
+    let reset = move |_| {
+        price_change.set(String::new());
+        commission.set(String::new());
+        acquiring.set(String::new());
+        logistics.set(String::new());
+        cost_change.set(String::new());
+        price_overrides.set(HashMap::new());
+        calculate();
+    };
+
+    view! {
+        <PageFrame page_id="d409_margin_scenario--dashboard" category="dashboard" class="page--wide">
+            <style>
+                ".d409-shell{display:flex;flex-direction:column;gap:12px;height:100%}
+                .d409-toolbar{display:flex;gap:10px;align-items:end;flex-wrap:wrap;padding:6px 0}
+                .d409-field{display:flex;flex-direction:column;gap:4px;min-width:120px}
+                .d409-field label{font-size:12px;color:var(--color-text-secondary)}
+                .d409-field input,.d409-field select,.d409-field textarea{min-height:32px;border:1px solid var(--color-border);border-radius:6px;padding:0 8px;background:var(--color-surface);color:var(--color-text-primary)}
+                .d409-field textarea{padding:6px 8px;min-width:280px;resize:vertical}
+                .d409-btn{height:32px;border:1px solid var(--color-border);border-radius:6px;background:var(--color-surface);color:var(--color-text-primary);padding:0 12px;cursor:pointer}
+                .d409-summary{display:flex;gap:18px;flex-wrap:wrap;border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;padding:10px;background:var(--color-surface)}
+                .d409-summary span{font-size:12px;color:var(--color-text-secondary)}
+                .d409-summary strong{font-size:14px;color:var(--color-text-primary);font-variant-numeric:tabular-nums}
+                .d409-table-wrap{overflow:auto;border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface)}
+                .d409-table{width:100%;border-collapse:collapse;font-size:13px}
+                .d409-table th{position:sticky;top:0;background:var(--color-surface);z-index:1;text-align:left;border-bottom:1px solid var(--color-border);padding:8px;color:var(--color-text-secondary);font-weight:600;white-space:nowrap}
+                .d409-table td{border-bottom:1px solid var(--color-border-light,var(--color-border));padding:6px 8px}
+                .d409-num{text-align:right;font-variant-numeric:tabular-nums;white-space:nowrap}
+                .d409-scenario{background:color-mix(in srgb,var(--color-brand,#2563eb) 5%,transparent)}
+                .d409-price{width:90px;height:28px;border:1px solid var(--color-border);border-radius:6px;text-align:right;padding:0 6px;background:var(--color-surface);color:var(--color-text-primary)}
+                .d409-up{color:#16a34a}
+                .d409-down{color:#dc2626}
+                .d409-state{padding:18px;color:var(--color-text-secondary)}"
+            </style>
+            <div class="d409-shell">
+                <div>
+                    <h1 style="margin:0;font-size:20px;">"Сценарий маржи"</h1>
+                    <div style="color:var(--color-text-secondary);font-size:13px;">
+                        "Факт за период — из финотчёта WB и дилерских цен заказов; пустое допущение оставляет фактическое значение"
+                    </div>
+                </div>
+
+                <div class="d409-toolbar">
+                    <div class="d409-field">
+                        <label>"nmId"</label>
+                        <textarea
+                            rows="2"
+                            placeholder="Через запятую или с новой строки"
+                            prop:value=move || nm_ids_text.get()
+                            on:input=move |ev| nm_ids_text.set(event_target_value(&ev))
+                        ></textarea>
+                    </div>
+                    <div class="d409-field">
+                        <label>"Факт с"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_from.get()
+                            on:input=move |ev| date_from.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d409-field">
+                        <label>"по"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_to.get()
+                            on:input=move |ev| date_to.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d409-field">
+                        <label>"Кабинет"</label>
+                        <select
+                            prop:value=move || connection_mp_ref.get()
+                            on:change=move |ev| connection_mp_ref.set(event_target_value(&ev))
+                        >
+                            <option value="">"Все кабинеты"</option>
+                            <For
+                                each=move || cabinets.get()
+                                key=|(id, _)| id.clone()
+                                children=move |(id, label)| {
+                                    view! { <option value=id.clone()>{label}</option> }
+                                }
+                            />
+                        </select>
+                    </div>
+                </div>
+
+                <div class="d409-toolbar">
+                    <AssumptionField label="Цена, изменение %" value=price_change />
+                    <AssumptionField label="Комиссия, %" value=commission />
+                    <AssumptionField label="Эквайринг, %" value=acquiring />
+                    <AssumptionField label="Логистика, ₽/шт" value=logistics />
+                    <AssumptionField label="Себестоимость, изменение %" value=cost_change />
+                    <button class="d409-btn" on:click=move |_| calculate() disabled=move || loading.get()>
+                        {move || if loading.get() { "Расчёт..." } else { "Рассчитать" }}
+                    </button>
+                    <button class="d409-btn" on:click=reset>"Сбросить допущения"</button>
+                </div>
+
+                {move || error.get().map(|message| view! {
+                    <div class="d409-state">{message}</div>
+                })}
+
+                {move || data.get().map(|response| {
+                    let totals = response.totals.clone();
+                    let delta = totals.scenario_profit - totals.actual_profit;
+                    view! {
+                        <div class="d409-summary">
+                            <span>"Продано за период, шт: " <strong>{format_number(totals.sold_qty, 0)}</strong></span>
+                            <span>"Прибыль факт: " <strong>{format_money(totals.actual_profit)}</strong></span>
+                            <span>"Прибыль сценарий: " <strong>{format_money(totals.scenario_profit)}</strong></span>
+                            <span>
+                                "Разница: "
+                                <strong class:d409-up={delta > 0.0} class:d409-down={delta < 0.0}>
+                                    {format_money(delta)}
+                                </strong>
+                            </span>
+                        </div>
+                    }
+                })}
+
+                <div class="d409-table-wrap">
+                    <table class="d409-table">
+                        <thead>
+                            <tr>
+                                <th>"nmId"</th>
+                                <th>"Артикул"</th>
+                                <th title="ABC/XYZ-класс последнего квартала">"ABC/XYZ"</th>
+                                <th class="d409-num">"Продано"</th>
+                                <th class="d409-num">"Цена"</th>
+                                <th class="d409-num d409-scenario">"Цена сц."</th>
+                                <th class="d409-num">"Комиссия %"</th>
+                                <th class="d409-num">"Эквайринг %"</th>
+                                <th class="d409-num">"Логистика/шт"</th>
+                                <th class="d409-num" title="Хранение и приёмка WB, распределённые на SKU">"Хранение/шт"</th>
+                                <th class="d409-num">"Себестоимость"</th>
+                                <th class="d409-num">"Прибыль/шт"</th>
+                                <th class="d409-num d409-scenario">"Прибыль/шт сц."</th>
+                                <th class="d409-num">"Наценка %"</th>
+                                <th class="d409-num d409-scenario">"Наценка % сц."</th>
+                                <th class="d409-num">"margin_pro заказов"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {move || {
+                                let Some(response) = data.get() else {
+                                    return view! {
+                                        <tr><td class="d409-state" colspan="16">"Укажите nmId и нажмите «Рассчитать»."</td></tr>
+                                    }.into_any();
+                                };
+                                response.rows.into_iter().map(|row| {
+                                    let nm_id = row.nm_id;
+                                    let profit_delta = row.scenario_profit_per_unit - row.actual_profit_per_unit;
+                                    let pair = |actual: String, scenario: String| {
+                                        if actual == scenario {
+                                            actual
+                                        } else {
+                                            format!("{actual} → {scenario}")
+                                        }
+                                    };
+                                    view! {
+                                        <tr>
+                                            <td>{nm_id}</td>
+                                            <td>{row.article.clone()}</td>
+                                            <td>{abc_xyz_badge(row.abc_xyz.clone())}</td>
+                                            <td class="d409-num">{format_number(row.sold_qty, 0)}</td>
+                                            <td class="d409-num">{format_money(row.actual.price)}</td>
+                                            <td class="d409-num d409-scenario">
+                                                <input
+                                                    class="d409-price"
+                                                    type="text"
+                                                    inputmode="decimal"
+                                                    prop:value=format!("{:.2}", row.scenario.price)
+                                                    on:change=move |ev| set_price_override(nm_id, event_target_value(&ev))
+                                                />
+                                            </td>
+                                            <td class="d409-num">
+                                                {pair(format_percent(row.actual.commission_percent, 1), format_percent(row.scenario.commission_percent, 1))}
+                                            </td>
+                                            <td class="d409-num">
+                                                {pair(format_percent(row.actual.acquiring_percent, 1), format_percent(row.scenario.acquiring_percent, 1))}
+                                            </td>
+                                            <td class="d409-num">
+                                                {pair(format_money(row.actual.logistics_per_unit), format_money(row.scenario.logistics_per_unit))}
+                                            </td>
+                                            <td class="d409-num">{format_money(row.actual.storage_per_unit)}</td>
+                                            <td class="d409-num">
+                                                {pair(format_money(row.actual.cost_per_unit), format_money(row.scenario.cost_per_unit))}
+                                            </td>
+                                            <td class="d409-num">{format_money(row.actual_profit_per_unit)}</td>
+                                            <td
+                                                class="d409-num d409-scenario"
+                                                class:d409-up={profit_delta > 0.0}
+                                                class:d409-down={profit_delta < 0.0}
+                                            >
+                                                {format_money(row.scenario_profit_per_unit)}
+                                            </td>
+                                            <td class="d409-num">{format_percent_opt(row.actual_markup_percent, 1)}</td>
+                                            <td class="d409-num d409-scenario">{format_percent_opt(row.scenario_markup_percent, 1)}</td>
+                                            <td class="d409-num">{format_percent_opt(row.orders_margin_pro, 1)}</td>
+                                        </tr>
+                                    }
+                                }).collect_view().into_any()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+            </div>
+        </PageFrame>
+    }
+}
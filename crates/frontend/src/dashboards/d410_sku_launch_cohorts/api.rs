@@ -0,0 +1,38 @@
+use contracts::dashboards::d410_sku_launch_cohorts::SkuLaunchCohortsResponse;
+use gloo_net::http::Request;
+
+pub async fn get_sku_launch_cohorts(
+    date_from: &str,
+    date_to: &str,
+    connection_mp_ref: &str,
+    horizon_months: u32,
+    launch_cost_per_sku: Option<f64>,
+) -> Result<SkuLaunchCohortsResponse, String> {
+    let mut params = vec![
+        format!("date_from={}", urlencoding::encode(date_from.trim())),
+        format!("date_to={}", urlencoding::encode(date_to.trim())),
+        format!("horizon_months={horizon_months}"),
+    ];
+    if !connection_mp_ref.trim().is_empty() {
+        params.push(format!(
+            "connection_mp_ref={}",
+            urlencoding::encode(connection_mp_ref.trim())
+        ));
+    }
+    if let Some(cost) = launch_cost_per_sku {
+        params.push(format!("launch_cost_per_sku={cost}"));
+    }
+
+    let url = format!("/api/dashboards/sku-launch-cohorts?{}", params.join("&"));
+    let response = Request::get(&url)
+        .send()
+        .await
+        .map_err(|error| format!("Request failed: {error}"))?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|error| format!("Failed to parse response: {error}"))
+}
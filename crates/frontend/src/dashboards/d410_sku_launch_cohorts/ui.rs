@@ -0,0 +1,323 @@
+use crate::dashboards::d410_sku_launch_cohorts::api;
+use crate::shared::api_utils::api_base;
+use crate::shared::money_format::{format_money, format_number};
+use crate::shared::page_frame::PageFrame;
+use chrono::{Datelike, Months, Utc};
+use contracts::dashboards::d410_sku_launch_cohorts::{
+    CohortMonthCell, SkuLaunchCohortsResponse, DEFAULT_HORIZON_MONTHS, MAX_HORIZON_MONTHS,
+};
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::common::AggregateId;
+use gloo_net::http::Request;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Показатель в ячейках тепловой карты.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CohortMetric {
+    CumulativeRevenue,
+    CumulativeMargin,
+    MarginPerSku,
+}
+
+impl CohortMetric {
+    fn code(self) -> &'static str {
+        match self {
+            Self::CumulativeRevenue => "revenue",
+            Self::CumulativeMargin => "margin",
+            Self::MarginPerSku => "margin_per_sku",
+        }
+    }
+
+    fn from_code(code: &str) -> Self {
+        match code {
+            "revenue" => Self::CumulativeRevenue,
+            "margin" => Self::CumulativeMargin,
+            _ => Self::MarginPerSku,
+        }
+    }
+
+    fn format(self, value: f64) -> String {
+        match self {
+            Self::MarginPerSku => format_number(value, 0),
+            _ => format_money(value),
+        }
+    }
+
+    fn value(self, cell: &CohortMonthCell) -> f64 {
+        match self {
+            Self::CumulativeRevenue => cell.cumulative_revenue,
+            Self::CumulativeMargin => cell.cumulative_margin,
+            Self::MarginPerSku => cell.cumulative_margin_per_sku,
+        }
+    }
+}
+
+/// Период запусков по умолчанию — последние 12 месяцев, включая текущий.
+fn default_date_from() -> String {
+    let today = Utc::now().date_naive();
+    let first = today.with_day(1).unwrap_or(today);
+    first
+        .checked_sub_months(Months::new(11))
+        .unwrap_or(first)
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn today() -> String {
+    Utc::now().date_naive().format("%Y-%m-%d").to_string()
+}
+
+/// Фон ячейки: зелёный для плюса, красный для минуса, насыщенность — от максимума по модулю.
+fn heat_style(value: f64, max_abs: f64) -> String {
+    if max_abs <= 0.0 || value == 0.0 {
+        return String::new();
+    }
+    let intensity = (value.abs() / max_abs * 55.0).round().clamp(4.0, 55.0);
+    let color = if value > 0.0 { "#16a34a" } else { "#dc2626" };
+    format!("background:color-mix(in srgb,{color} {intensity}%,transparent)")
+}
+
+#[component]
+pub fn SkuLaunchCohortsDashboard() -> impl IntoView {
+    let date_from = RwSignal::new(default_date_from());
+    let date_to = RwSignal::new(today());
+    let connection_mp_ref = RwSignal::new(String::new());
+    let horizon = RwSignal::new(DEFAULT_HORIZON_MONTHS);
+    let launch_cost = RwSignal::new(String::new());
+    let metric = RwSignal::new(CohortMetric::MarginPerSku);
+    let cabinets = RwSignal::new(Vec::<(String, String)>::new());
+    let data = RwSignal::new(None::<SkuLaunchCohortsResponse>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    spawn_local(async move {
+        let url = format!("{}/api/connection_mp", api_base());
+        let Ok(resp) = Request::get(&url).send().await else {
+            return;
+        };
+        if !resp.ok() {
+            return;
+        }
+        if let Ok(data) = resp.json::<Vec<ConnectionMP>>().await {
+            let mut opts: Vec<(String, String)> = data
+                .into_iter()
+                .map(|conn| {
+                    let label = if conn.base.description.trim().is_empty() {
+                        conn.base.code.clone()
+                    } else {
+                        conn.base.description.clone()
+                    };
+                    (conn.base.id.as_string(), label)
+                })
+                .collect();
+            opts.sort_by(|a, b| a.1.cmp(&b.1));
+            cabinets.set(opts);
+        }
+    });
+
+    let load = move || {
+        let df = date_from.get_untracked();
+        let dt = date_to.get_untracked();
+        let conn = connection_mp_ref.get_untracked();
+        let months = horizon.get_untracked();
+        let cost = launch_cost
+            .get_untracked()
+            .trim()
+            .replace(',', ".")
+            .parse::<f64>()
+            .ok()
+            .filter(|cost| *cost > 0.0);
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::get_sku_launch_cohorts(&df, &dt, &conn, months, cost).await {
+                Ok(response) => data.set(Some(response)),
+                Err(message) => error.set(Some(message)),
+            }
+            loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| load());
+
+    view! {
+        <PageFrame page_id="d410_sku_launch_cohorts--dashboard" category="dashboard" class="page--wide">
+            <style>
+                ".d410-shell{display:flex;flex-direction:column;gap:12px;height:100%}
+                .d410-toolbar{display:flex;gap:10px;align-items:end;flex-wrap:wrap;padding:10px 0}
+                .d410-field{display:flex;flex-direction:column;gap:4px;min-width:140px}
+                .d410-field label{font-size:12px;color:var(--color-text-secondary)}
+                .d410-field input,.d410-field select{height:32px;border:1px solid var(--color-border);border-radius:6px;padding:0 8px;background:var(--color-surface);color:var(--color-text-primary)}
+                .d410-btn{height:32px;border:1px solid var(--color-border);border-radius:6px;background:var(--color-surface);color:var(--color-text-primary);padding:0 12px;cursor:pointer}
+                .d410-table-wrap{overflow:auto;border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface)}
+                .d410-table{border-collapse:collapse;font-size:12px}
+                .d410-table th{position:sticky;top:0;background:var(--color-surface);z-index:1;border-bottom:1px solid var(--color-border);padding:6px 8px;color:var(--color-text-secondary);font-weight:600;white-space:nowrap}
+                .d410-table td{border-bottom:1px solid var(--color-border-light,var(--color-border));padding:6px 8px;white-space:nowrap}
+                .d410-num{text-align:right;font-variant-numeric:tabular-nums}
+                .d410-cell--payback{outline:2px solid #16a34a;outline-offset:-2px;font-weight:600}
+                .d410-legend{font-size:12px;color:var(--color-text-secondary)}
+                .d410-state{padding:18px;color:var(--color-text-secondary)}"
+            </style>
+            <div class="d410-shell">
+                <div>
+                    <h1 style="margin:0;font-size:20px;">"Когорты запусков SKU"</h1>
+                    <div style="color:var(--color-text-secondary);font-size:13px;">
+                        "Товары сгруппированы по месяцу первой продажи; накопленная выручка и валовая маржа (выручка минус себестоимость) по месяцам жизни"
+                    </div>
+                </div>
+
+                <div class="d410-toolbar">
+                    <div class="d410-field">
+                        <label>"Запуски с"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_from.get()
+                            on:input=move |ev| date_from.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d410-field">
+                        <label>"по"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_to.get()
+                            on:input=move |ev| date_to.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d410-field">
+                        <label>"Кабинет"</label>
+                        <select
+                            prop:value=move || connection_mp_ref.get()
+                            on:change=move |ev| connection_mp_ref.set(event_target_value(&ev))
+                        >
+                            <option value="">"Все кабинеты"</option>
+                            <For
+                                each=move || cabinets.get()
+                                key=|(id, _)| id.clone()
+                                children=move |(id, label)| {
+                                    view! { <option value=id.clone()>{label}</option> }
+                                }
+                            />
+                        </select>
+                    </div>
+                    <div class="d410-field">
+                        <label>"Горизонт, мес."</label>
+                        <input
+                            type="number"
+                            min="1"
+                            max=MAX_HORIZON_MONTHS.to_string()
+                            prop:value=move || horizon.get().to_string()
+                            on:input=move |ev| {
+                                let months = event_target_value(&ev)
+                                    .parse::<u32>()
+                                    .unwrap_or(DEFAULT_HORIZON_MONTHS)
+                                    .clamp(1, MAX_HORIZON_MONTHS);
+                                horizon.set(months);
+                            }
+                        />
+                    </div>
+                    <div class="d410-field">
+                        <label>"Вложения в запуск SKU, ₽"</label>
+                        <input
+                            type="text"
+                            inputmode="decimal"
+                            placeholder="не задано"
+                            prop:value=move || launch_cost.get()
+                            on:input=move |ev| launch_cost.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d410-field">
+                        <label>"Показатель"</label>
+                        <select
+                            prop:value=move || metric.get().code()
+                            on:change=move |ev| metric.set(CohortMetric::from_code(&event_target_value(&ev)))
+                        >
+                            <option value="margin_per_sku">"Накопленная маржа на SKU"</option>
+                            <option value="margin">"Накопленная маржа"</option>
+                            <option value="revenue">"Накопленная выручка"</option>
+                        </select>
+                    </div>
+                    <button class="d410-btn" on:click=move |_| load() disabled=move || loading.get()>
+                        {move || if loading.get() { "Загрузка..." } else { "Обновить" }}
+                    </button>
+                </div>
+
+                <div class="d410-legend">
+                    "M0 — месяц запуска. Рамкой отмечен месяц, в котором накопленная маржа на SKU покрыла вложения в запуск."
+                </div>
+
+                {move || error.get().map(|message| view! {
+                    <div class="d410-state">{message}</div>
+                })}
+
+                {move || data.get().map(|response| {
+                    if response.cohorts.is_empty() {
+                        return view! {
+                            <div class="d410-state">"Нет запусков за выбранный период."</div>
+                        }.into_any();
+                    }
+                    let metric_now = metric.get();
+                    let max_abs = response
+                        .cohorts
+                        .iter()
+                        .flat_map(|cohort| cohort.cells.iter())
+                        .map(|cell| metric_now.value(cell).abs())
+                        .fold(0.0_f64, f64::max);
+                    let offsets = response.horizon_months;
+                    view! {
+                        <div class="d410-table-wrap">
+                            <table class="d410-table">
+                                <thead>
+                                    <tr>
+                                        <th>"Запуск"</th>
+                                        <th class="d410-num">"SKU"</th>
+                                        <th class="d410-num">"Окупаемость"</th>
+                                        {(0..offsets).map(|offset| view! {
+                                            <th class="d410-num">{format!("M{offset}")}</th>
+                                        }).collect_view()}
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {response.cohorts.into_iter().map(|cohort| {
+                                        let payback = cohort.payback_offset;
+                                        let filled = cohort.cells.len() as u32;
+                                        view! {
+                                            <tr>
+                                                <td>{cohort.launch_month.clone()}</td>
+                                                <td class="d410-num">{cohort.sku_count}</td>
+                                                <td class="d410-num">
+                                                    {payback.map(|offset| format!("M{offset}")).unwrap_or_else(|| "—".to_string())}
+                                                </td>
+                                                {cohort.cells.into_iter().map(|cell| {
+                                                    let value = metric_now.value(&cell);
+                                                    let title = format!(
+                                                        "{}: активных SKU {}, выручка {}, маржа {}",
+                                                        cell.month,
+                                                        cell.active_skus,
+                                                        format_money(cell.revenue),
+                                                        format_money(cell.margin)
+                                                    );
+                                                    view! {
+                                                        <td
+                                                            class="d410-num"
+                                                            class:d410-cell--payback={payback == Some(cell.offset)}
+                                                            style=heat_style(value, max_abs)
+                                                            title=title
+                                                        >
+                                                            {metric_now.format(value)}
+                                                        </td>
+                                                    }
+                                                }).collect_view()}
+                                                {(filled..offsets).map(|_| view! { <td></td> }).collect_view()}
+                                            </tr>
+                                        }
+                                    }).collect_view()}
+                                </tbody>
+                            </table>
+                        </div>
+                    }.into_any()
+                })}
+            </div>
+        </PageFrame>
+    }
+}
@@ -0,0 +1,58 @@
+use contracts::domain::a007_marketplace_product::aggregate::{
+    ExplainSalesAnomalyRequest, SalesAnomalyListResponse,
+};
+use gloo_net::http::Request;
+
+pub async fn get_sales_anomalies(
+    date_from: &str,
+    date_to: &str,
+    connection_mp_ref: &str,
+    direction: &str,
+    status: &str,
+    totals_only: bool,
+) -> Result<SalesAnomalyListResponse, String> {
+    let mut params = vec![format!("totals_only={totals_only}")];
+    for (key, value) in [
+        ("date_from", date_from),
+        ("date_to", date_to),
+        ("connection_mp_ref", connection_mp_ref),
+        ("direction", direction),
+        ("status", status),
+    ] {
+        if !value.trim().is_empty() {
+            params.push(format!("{key}={}", urlencoding::encode(value.trim())));
+        }
+    }
+
+    let url = format!("/api/a007/sales-anomalies?{}", params.join("&"));
+    let response = Request::get(&url)
+        .send()
+        .await
+        .map_err(|error| format!("Request failed: {error}"))?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|error| format!("Failed to parse response: {error}"))
+}
+
+pub async fn explain_sales_anomaly(id: &str, explanation: &str) -> Result<(), String> {
+    let url = format!(
+        "/api/a007/sales-anomalies/{}/explain",
+        urlencoding::encode(id)
+    );
+    let response = Request::post(&url)
+        .json(&ExplainSalesAnomalyRequest {
+            explanation: explanation.to_string(),
+        })
+        .map_err(|error| format!("Failed to serialize request: {error}"))?
+        .send()
+        .await
+        .map_err(|error| format!("Request failed: {error}"))?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    Ok(())
+}
@@ -0,0 +1,385 @@
+use crate::dashboards::d411_sales_anomalies::api;
+use crate::layout::global_context::AppGlobalContext;
+use crate::shared::api_utils::api_base;
+use crate::shared::components::ui::badge::Badge;
+use crate::shared::money_format::{format_money, format_number, format_qty};
+use crate::shared::page_frame::PageFrame;
+use chrono::{Duration, Utc};
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::a007_marketplace_product::aggregate::{
+    SalesAnomalyDirection, SalesAnomalyDto, SalesAnomalyListResponse,
+};
+use contracts::domain::common::AggregateId;
+use gloo_net::http::Request;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Период по умолчанию — последние 30 дней.
+fn default_date_from() -> String {
+    (Utc::now().date_naive() - Duration::days(30))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn today() -> String {
+    Utc::now().date_naive().format("%Y-%m-%d").to_string()
+}
+
+fn direction_variant(direction: SalesAnomalyDirection) -> String {
+    match direction {
+        SalesAnomalyDirection::Spike => "success",
+        SalesAnomalyDirection::Drop => "error",
+    }
+    .to_string()
+}
+
+#[component]
+pub fn SalesAnomaliesDashboard() -> impl IntoView {
+    let tabs = expect_context::<AppGlobalContext>();
+    let date_from = RwSignal::new(default_date_from());
+    let date_to = RwSignal::new(today());
+    let connection_mp_ref = RwSignal::new(String::new());
+    let direction = RwSignal::new(String::new());
+    let status = RwSignal::new("open".to_string());
+    let totals_only = RwSignal::new(false);
+    let cabinets = RwSignal::new(Vec::<(String, String)>::new());
+    let data = RwSignal::new(None::<SalesAnomalyListResponse>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+    // Аномалия, для которой открыто поле пояснения, и текст пояснения.
+    let editing = RwSignal::new(None::<String>);
+    let draft = RwSignal::new(String::new());
+
+    spawn_local(async move {
+        let url = format!("{}/api/connection_mp", api_base());
+        let Ok(resp) = Request::get(&url).send().await else {
+            return;
+        };
+        if !resp.ok() {
+            return;
+        }
+        if let Ok(data) = resp.json::<Vec<ConnectionMP>>().await {
+            let mut opts: Vec<(String, String)> = data
+                .into_iter()
+                .map(|conn| {
+                    let label = if conn.base.description.trim().is_empty() {
+                        conn.base.code.clone()
+                    } else {
+                        conn.base.description.clone()
+                    };
+                    (conn.base.id.as_string(), label)
+                })
+                .collect();
+            opts.sort_by(|a, b| a.1.cmp(&b.1));
+            cabinets.set(opts);
+        }
+    });
+
+    let load = move || {
+        let df = date_from.get_untracked();
+        let dt = date_to.get_untracked();
+        let conn = connection_mp_ref.get_untracked();
+        let dir = direction.get_untracked();
+        let st = status.get_untracked();
+        let totals = totals_only.get_untracked();
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::get_sales_anomalies(&df, &dt, &conn, &dir, &st, totals).await {
+                Ok(response) => data.set(Some(response)),
+                Err(message) => error.set(Some(message)),
+            }
+            loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| load());
+
+    let save_explanation = move |id: String| {
+        let text = draft.get_untracked().trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        spawn_local(async move {
+            match api::explain_sales_anomaly(&id, &text).await {
+                Ok(()) => {
+                    editing.set(None);
+                    draft.set(String::new());
+                    load();
+                }
+                Err(message) => error.set(Some(message)),
+            }
+        });
+    };
+
+    let cabinet_label = move |id: &str| {
+        cabinets.with(|list| {
+            list.iter()
+                .find(|(cabinet_id, _)| cabinet_id == id)
+                .map(|(_, label)| label.clone())
+                .unwrap_or_else(|| id.to_string())
+        })
+    };
+
+    let render_row = move |row: SalesAnomalyDto| {
+        let id = row.id.clone();
+        let id_for_edit = row.id.clone();
+        let id_for_save = row.id.clone();
+        let product_cell = match row.marketplace_product_ref.clone() {
+            Some(product_ref) => {
+                let label = row
+                    .article
+                    .clone()
+                    .filter(|article| !article.is_empty())
+                    .unwrap_or_else(|| product_ref.clone());
+                let tab_key = format!("a007_marketplace_product_details_{product_ref}");
+                let tab_title = format!("Товар МП {label}");
+                let tabs_for_click = tabs.clone();
+                view! {
+                    <button
+                        class="d411-link"
+                        title=row.product_name.clone().unwrap_or_default()
+                        on:click=move |_| tabs_for_click.open_tab(&tab_key, &tab_title)
+                    >
+                        {label}
+                    </button>
+                }
+                .into_any()
+            }
+            None => view! { <span class="d411-total">"Итог кабинета"</span> }.into_any(),
+        };
+        let explanation_cell = match row.explanation.clone() {
+            Some(text) => {
+                let who = format!(
+                    "{} · {}",
+                    row.explained_by.clone().unwrap_or_default(),
+                    row.explained_at
+                        .clone()
+                        .unwrap_or_default()
+                        .chars()
+                        .take(10)
+                        .collect::<String>()
+                );
+                view! {
+                    <div>
+                        <div>{text}</div>
+                        <div class="d411-muted">{who}</div>
+                    </div>
+                }
+                .into_any()
+            }
+            None => view! {
+                <Show
+                    when=move || editing.get().as_deref() == Some(id.as_str())
+                    fallback=move || {
+                        let id = id_for_edit.clone();
+                        view! {
+                            <button
+                                class="d411-btn d411-btn--small"
+                                on:click=move |_| {
+                                    draft.set(String::new());
+                                    editing.set(Some(id.clone()));
+                                }
+                            >
+                                "Объяснить"
+                            </button>
+                        }
+                    }
+                >
+                    {
+                        let id = id_for_save.clone();
+                        view! {
+                            <div class="d411-explain">
+                                <input
+                                    type="text"
+                                    placeholder="Причина: акция, остатки, сбой выгрузки…"
+                                    prop:value=move || draft.get()
+                                    on:input=move |ev| draft.set(event_target_value(&ev))
+                                />
+                                <button
+                                    class="d411-btn d411-btn--small"
+                                    disabled=move || draft.get().trim().is_empty()
+                                    on:click={
+                                        let id = id.clone();
+                                        move |_| save_explanation(id.clone())
+                                    }
+                                >
+                                    "Сохранить"
+                                </button>
+                                <button class="d411-btn d411-btn--small" on:click=move |_| editing.set(None)>
+                                    "Отмена"
+                                </button>
+                            </div>
+                        }
+                    }
+                </Show>
+            }
+            .into_any(),
+        };
+        view! {
+            <tr class:d411-row--explained=row.explanation.is_some()>
+                <td>{row.sale_date.clone()}</td>
+                <td>{cabinet_label(&row.connection_mp_ref)}</td>
+                <td>{product_cell}</td>
+                <td>
+                    <Badge variant=direction_variant(row.direction)>{row.direction.label()}</Badge>
+                </td>
+                <td class="d411-num">{format_qty(row.actual_qty)}</td>
+                <td class="d411-num">{format_number(row.expected_qty, 1)}</td>
+                <td class="d411-num">{format_number(row.z_score, 1)}</td>
+                <td class="d411-num">{format_money(row.actual_revenue)}</td>
+                <td class="d411-num">{format_money(row.expected_revenue)}</td>
+                <td>{explanation_cell}</td>
+            </tr>
+        }
+    };
+
+    view! {
+        <PageFrame page_id="d411_sales_anomalies--dashboard" category="dashboard" class="page--wide">
+            <style>
+                ".d411-shell{display:flex;flex-direction:column;gap:12px;height:100%}
+                .d411-toolbar{display:flex;gap:10px;align-items:end;flex-wrap:wrap;padding:10px 0}
+                .d411-field{display:flex;flex-direction:column;gap:4px;min-width:140px}
+                .d411-field label{font-size:12px;color:var(--color-text-secondary)}
+                .d411-field input,.d411-field select,.d411-explain input{height:32px;border:1px solid var(--color-border);border-radius:6px;padding:0 8px;background:var(--color-surface);color:var(--color-text-primary)}
+                .d411-check{display:flex;gap:6px;align-items:center;height:32px;font-size:13px}
+                .d411-btn{height:32px;border:1px solid var(--color-border);border-radius:6px;background:var(--color-surface);color:var(--color-text-primary);padding:0 12px;cursor:pointer}
+                .d411-btn--small{height:26px;padding:0 8px;font-size:12px}
+                .d411-summary{font-size:13px;color:var(--color-text-secondary)}
+                .d411-summary strong{color:var(--color-text-primary)}
+                .d411-table-wrap{overflow:auto;border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface)}
+                .d411-table{width:100%;border-collapse:collapse;font-size:13px}
+                .d411-table th{position:sticky;top:0;background:var(--color-surface);z-index:1;text-align:left;border-bottom:1px solid var(--color-border);padding:8px;color:var(--color-text-secondary);font-weight:600}
+                .d411-table td{border-bottom:1px solid var(--color-border-light,var(--color-border));padding:7px 8px;vertical-align:top}
+                .d411-num{text-align:right;font-variant-numeric:tabular-nums}
+                .d411-row--explained{opacity:.7}
+                .d411-explain{display:flex;gap:6px;align-items:center}
+                .d411-explain input{min-width:240px;height:26px}
+                .d411-muted{font-size:11px;color:var(--color-text-secondary)}
+                .d411-total{font-weight:600}
+                .d411-link{border:0;background:transparent;color:var(--color-brand,#2563eb);cursor:pointer;font:inherit;padding:0}
+                .d411-link:hover{text-decoration:underline}
+                .d411-state{padding:18px;color:var(--color-text-secondary)}"
+            </style>
+            <div class="d411-shell">
+                <div>
+                    <h1 style="margin:0;font-size:20px;">"Аномалии продаж"</h1>
+                    <div style="color:var(--color-text-secondary);font-size:13px;">
+                        "Дни, когда продажи товара или кабинета сильно отличались от того же дня недели в предыдущие недели (task028)"
+                    </div>
+                </div>
+
+                <div class="d411-toolbar">
+                    <div class="d411-field">
+                        <label>"Дни с"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_from.get()
+                            on:input=move |ev| date_from.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d411-field">
+                        <label>"по"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_to.get()
+                            on:input=move |ev| date_to.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d411-field">
+                        <label>"Кабинет"</label>
+                        <select
+                            prop:value=move || connection_mp_ref.get()
+                            on:change=move |ev| connection_mp_ref.set(event_target_value(&ev))
+                        >
+                            <option value="">"Все кабинеты"</option>
+                            <For
+                                each=move || cabinets.get()
+                                key=|(id, _)| id.clone()
+                                children=move |(id, label)| {
+                                    view! { <option value=id.clone()>{label}</option> }
+                                }
+                            />
+                        </select>
+                    </div>
+                    <div class="d411-field">
+                        <label>"Направление"</label>
+                        <select
+                            prop:value=move || direction.get()
+                            on:change=move |ev| direction.set(event_target_value(&ev))
+                        >
+                            <option value="">"Все"</option>
+                            <option value="spike">"Всплески"</option>
+                            <option value="drop">"Провалы"</option>
+                        </select>
+                    </div>
+                    <div class="d411-field">
+                        <label>"Статус"</label>
+                        <select
+                            prop:value=move || status.get()
+                            on:change=move |ev| status.set(event_target_value(&ev))
+                        >
+                            <option value="open">"Не разобраны"</option>
+                            <option value="explained">"Объяснены"</option>
+                            <option value="">"Все"</option>
+                        </select>
+                    </div>
+                    <label class="d411-check">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || totals_only.get()
+                            on:change=move |ev| totals_only.set(event_target_checked(&ev))
+                        />
+                        "Только итоги кабинетов"
+                    </label>
+                    <button class="d411-btn" on:click=move |_| load() disabled=move || loading.get()>
+                        {move || if loading.get() { "Загрузка..." } else { "Обновить" }}
+                    </button>
+                </div>
+
+                {move || error.get().map(|message| view! {
+                    <div class="d411-state">{message}</div>
+                })}
+
+                {move || data.get().map(|response| view! {
+                    <div class="d411-summary">
+                        "Показано: " <strong>{response.items.len()}</strong>
+                        " · не разобрано всего: " <strong>{response.open_count}</strong>
+                    </div>
+                })}
+
+                <div class="d411-table-wrap">
+                    <table class="d411-table">
+                        <thead>
+                            <tr>
+                                <th>"День"</th>
+                                <th>"Кабинет"</th>
+                                <th>"Товар"</th>
+                                <th>"Отклонение"</th>
+                                <th class="d411-num">"Продано, шт."</th>
+                                <th class="d411-num">"Ожидалось, шт."</th>
+                                <th class="d411-num">"z"</th>
+                                <th class="d411-num">"Выручка"</th>
+                                <th class="d411-num">"Ожидалось, ₽"</th>
+                                <th>"Пояснение"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {move || {
+                                let rows = data.with(|response| {
+                                    response.as_ref().map(|r| r.items.clone()).unwrap_or_default()
+                                });
+                                if rows.is_empty() {
+                                    return view! {
+                                        <tr><td class="d411-state" colspan="10">"Аномалий за выбранный период нет."</td></tr>
+                                    }.into_any();
+                                }
+                                rows.into_iter().map(|row| render_row(row)).collect_view().into_any()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+            </div>
+        </PageFrame>
+    }
+}
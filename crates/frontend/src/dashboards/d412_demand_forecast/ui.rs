@@ -0,0 +1,409 @@
+use crate::dashboards::d412_demand_forecast::api;
+use crate::shared::api_utils::api_base;
+use crate::shared::money_format::{format_number, format_percent_opt};
+use crate::shared::page_frame::PageFrame;
+use chrono::{Duration, Utc};
+use contracts::dashboards::d412_demand_forecast::{
+    DemandForecastPoint, DemandForecastResponse, ForecastErrorMetrics, PRODUCT_SUMMARY_WEEKS,
+};
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::common::AggregateId;
+use gloo_net::http::Request;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Период факта по умолчанию — последние 26 недель.
+fn default_date_from() -> String {
+    (Utc::now().date_naive() - Duration::weeks(26))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn today() -> String {
+    Utc::now().date_naive().format("%Y-%m-%d").to_string()
+}
+
+/// Ломаная по точкам с заданным значением; разрывы ряда не соединяются.
+fn polyline_paths(
+    values: &[Option<f64>],
+    x: impl Fn(usize) -> f64,
+    y: impl Fn(f64) -> f64,
+) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut current = String::new();
+    for (index, value) in values.iter().enumerate() {
+        match value {
+            Some(value) => {
+                if !current.is_empty() {
+                    current.push(' ');
+                }
+                current.push_str(&format!("{:.1},{:.1}", x(index), y(*value)));
+            }
+            None if !current.is_empty() => paths.push(std::mem::take(&mut current)),
+            None => {}
+        }
+    }
+    if !current.is_empty() {
+        paths.push(current);
+    }
+    paths
+}
+
+// ---------------------------------------------------------------------------
+// График: факт, прогноз с упреждением и последний прогноз на будущее
+// ---------------------------------------------------------------------------
+
+fn forecast_chart(
+    history: &[DemandForecastPoint],
+    outlook: &[DemandForecastPoint],
+) -> impl IntoView {
+    let vb_w = 900.0_f64;
+    let vb_h = 260.0_f64;
+    let pad_left = 48.0;
+    let pad_right = 12.0;
+    let pad_top = 12.0;
+    let pad_bottom = 28.0;
+
+    // Будущие недели, уже попавшие в период факта, второй раз не рисуются.
+    let last_history = history.last().map(|point| point.week_start.clone());
+    let outlook: Vec<&DemandForecastPoint> = outlook
+        .iter()
+        .filter(|point| {
+            last_history
+                .as_ref()
+                .map_or(true, |last| point.week_start > *last)
+        })
+        .collect();
+    let weeks: Vec<&str> = history
+        .iter()
+        .chain(outlook.iter().copied())
+        .map(|point| point.week_start.as_str())
+        .collect();
+    let actual: Vec<Option<f64>> = history
+        .iter()
+        .map(|point| point.actual_qty)
+        .chain(outlook.iter().map(|_| None))
+        .collect();
+    let lead_forecast: Vec<Option<f64>> = history
+        .iter()
+        .map(|point| point.forecast_qty)
+        .chain(outlook.iter().map(|_| None))
+        .collect();
+    let future: Vec<Option<f64>> = history
+        .iter()
+        .map(|_| None)
+        .chain(outlook.iter().map(|point| point.forecast_qty))
+        .collect();
+
+    let max_value = actual
+        .iter()
+        .chain(lead_forecast.iter())
+        .chain(future.iter())
+        .flatten()
+        .fold(0.0_f64, |max, value| max.max(*value))
+        .max(1.0);
+    let count = weeks.len().max(2);
+    let plot_w = vb_w - pad_left - pad_right;
+    let plot_h = vb_h - pad_top - pad_bottom;
+    let x = move |index: usize| pad_left + plot_w * index as f64 / (count - 1) as f64;
+    let y = move |value: f64| pad_top + plot_h * (1.0 - value / max_value);
+
+    let line = |values: &[Option<f64>], class: &'static str| {
+        polyline_paths(values, x, y)
+            .into_iter()
+            .map(|points| view! { <polyline class=class points=points /> })
+            .collect_view()
+    };
+    let actual_lines = line(&actual, "d412-line d412-line--actual");
+    let lead_lines = line(&lead_forecast, "d412-line d412-line--lead");
+    let future_lines = line(&future, "d412-line d412-line--future");
+
+    let grid = (0..=4)
+        .map(|step| {
+            let value = max_value * step as f64 / 4.0;
+            let gy = y(value);
+            view! {
+                <line class="d412-grid" x1=pad_left x2=vb_w - pad_right y1=gy y2=gy />
+                <text class="d412-axis" x=pad_left - 6.0 y=gy text-anchor="end" dominant-baseline="central">
+                    {format_number(value, 0)}
+                </text>
+            }
+        })
+        .collect_view();
+    // Подписи недель — не чаще, чем помещаются.
+    let label_step = (weeks.len() / 10).max(1);
+    let labels = weeks
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| index % label_step == 0)
+        .map(|(index, week)| {
+            let label = week.get(5..).unwrap_or(week).to_string();
+            view! {
+                <text class="d412-axis" x=x(index) y=vb_h - 8.0 text-anchor="middle">
+                    {label}
+                </text>
+            }
+        })
+        .collect_view();
+
+    let view_box = format!("0 0 {vb_w} {vb_h}");
+    view! {
+        <svg
+            class="d412-chart"
+            viewBox=view_box
+            preserveAspectRatio="none"
+            role="img"
+            aria-label="Прогноз спроса и фактические продажи по неделям"
+        >
+            {grid}
+            {labels}
+            {actual_lines}
+            {lead_lines}
+            {future_lines}
+        </svg>
+    }
+}
+
+fn metrics_cards(metrics: &ForecastErrorMetrics, lead_weeks: u32) -> impl IntoView {
+    let bias_hint = match metrics.bias {
+        Some(bias) if bias > 0.0 => "прогноз завышает",
+        Some(bias) if bias < 0.0 => "прогноз занижает",
+        _ => "",
+    };
+    view! {
+        <div class="d412-cards">
+            <div class="d412-card">
+                <div class="d412-card-label">"Недель сравнения"</div>
+                <div class="d412-card-value">{metrics.weeks}</div>
+                <div class="d412-card-hint">{format!("прогноз за {lead_weeks} нед. до факта")}</div>
+            </div>
+            <div class="d412-card">
+                <div class="d412-card-label">"MAE, шт./нед."</div>
+                <div class="d412-card-value">{format_number(metrics.mae, 1)}</div>
+                <div class="d412-card-hint">"средняя абсолютная ошибка"</div>
+            </div>
+            <div class="d412-card">
+                <div class="d412-card-label">"WAPE"</div>
+                <div class="d412-card-value">{format_percent_opt(metrics.wape, 1)}</div>
+                <div class="d412-card-hint">"ошибка в % от факта"</div>
+            </div>
+            <div class="d412-card">
+                <div class="d412-card-label">"Смещение"</div>
+                <div class="d412-card-value">{format_percent_opt(metrics.bias, 1)}</div>
+                <div class="d412-card-hint">{bias_hint}</div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn DemandForecastDashboard() -> impl IntoView {
+    let date_from = RwSignal::new(default_date_from());
+    let date_to = RwSignal::new(today());
+    let connection_mp_ref = RwSignal::new(String::new());
+    let lead_weeks = RwSignal::new(1_u32);
+    let cabinets = RwSignal::new(Vec::<(String, String)>::new());
+    let data = RwSignal::new(None::<DemandForecastResponse>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    spawn_local(async move {
+        let url = format!("{}/api/connection_mp", api_base());
+        let Ok(resp) = Request::get(&url).send().await else {
+            return;
+        };
+        if !resp.ok() {
+            return;
+        }
+        if let Ok(data) = resp.json::<Vec<ConnectionMP>>().await {
+            let mut opts: Vec<(String, String)> = data
+                .into_iter()
+                .map(|conn| {
+                    let label = if conn.base.description.trim().is_empty() {
+                        conn.base.code.clone()
+                    } else {
+                        conn.base.description.clone()
+                    };
+                    (conn.base.id.as_string(), label)
+                })
+                .collect();
+            opts.sort_by(|a, b| a.1.cmp(&b.1));
+            cabinets.set(opts);
+        }
+    });
+
+    let load = move || {
+        let df = date_from.get_untracked();
+        let dt = date_to.get_untracked();
+        let conn = connection_mp_ref.get_untracked();
+        let lead = lead_weeks.get_untracked();
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::get_demand_forecast(&df, &dt, &conn, lead).await {
+                Ok(response) => data.set(Some(response)),
+                Err(message) => error.set(Some(message)),
+            }
+            loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| load());
+
+    view! {
+        <PageFrame page_id="d412_demand_forecast--dashboard" category="dashboard" class="page--wide">
+            <style>
+                ".d412-shell{display:flex;flex-direction:column;gap:12px;height:100%}
+                .d412-toolbar{display:flex;gap:10px;align-items:end;flex-wrap:wrap;padding:10px 0}
+                .d412-field{display:flex;flex-direction:column;gap:4px;min-width:140px}
+                .d412-field label{font-size:12px;color:var(--color-text-secondary)}
+                .d412-field input,.d412-field select{height:32px;border:1px solid var(--color-border);border-radius:6px;padding:0 8px;background:var(--color-surface);color:var(--color-text-primary)}
+                .d412-btn{height:32px;border:1px solid var(--color-border);border-radius:6px;background:var(--color-surface);color:var(--color-text-primary);padding:0 12px;cursor:pointer}
+                .d412-cards{display:grid;grid-template-columns:repeat(auto-fit,minmax(170px,1fr));gap:10px}
+                .d412-card{border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface);padding:10px 12px}
+                .d412-card-label{font-size:12px;color:var(--color-text-secondary)}
+                .d412-card-value{font-size:20px;font-weight:600;font-variant-numeric:tabular-nums}
+                .d412-card-hint{font-size:11px;color:var(--color-text-secondary);min-height:14px}
+                .d412-chart-wrap{border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface);padding:8px}
+                .d412-chart{width:100%;height:260px;display:block}
+                .d412-grid{stroke:var(--color-border-light,var(--color-border));stroke-width:1}
+                .d412-axis{font-size:10px;fill:var(--color-text-secondary)}
+                .d412-line{fill:none;stroke-width:2;vector-effect:non-scaling-stroke}
+                .d412-line--actual{stroke:#2563eb}
+                .d412-line--lead{stroke:#f59e0b;stroke-dasharray:6 4}
+                .d412-line--future{stroke:#16a34a;stroke-dasharray:6 4}
+                .d412-legend{display:flex;gap:16px;font-size:12px;color:var(--color-text-secondary)}
+                .d412-swatch{display:inline-block;width:18px;height:3px;margin-right:6px;vertical-align:middle}
+                .d412-table-wrap{overflow:auto;border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface)}
+                .d412-table{width:100%;border-collapse:collapse;font-size:12px}
+                .d412-table th{position:sticky;top:0;background:var(--color-surface);z-index:1;border-bottom:1px solid var(--color-border);padding:6px 8px;color:var(--color-text-secondary);font-weight:600;white-space:nowrap;text-align:left}
+                .d412-table td{border-bottom:1px solid var(--color-border-light,var(--color-border));padding:6px 8px;white-space:nowrap}
+                .d412-num{text-align:right !important;font-variant-numeric:tabular-nums}
+                .d412-state{padding:18px;color:var(--color-text-secondary)}"
+            </style>
+            <div class="d412-shell">
+                <div>
+                    <h1 style="margin:0;font-size:20px;">"Прогноз спроса"</h1>
+                    <div style="color:var(--color-text-secondary);font-size:13px;">
+                        "Недельные продажи в штуках против прогноза, сделанного заранее, и последний прогноз на ближайшие недели"
+                    </div>
+                </div>
+
+                <div class="d412-toolbar">
+                    <div class="d412-field">
+                        <label>"Факт с"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_from.get()
+                            on:input=move |ev| date_from.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d412-field">
+                        <label>"по"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_to.get()
+                            on:input=move |ev| date_to.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d412-field">
+                        <label>"Кабинет"</label>
+                        <select
+                            prop:value=move || connection_mp_ref.get()
+                            on:change=move |ev| connection_mp_ref.set(event_target_value(&ev))
+                        >
+                            <option value="">"Все кабинеты"</option>
+                            <For
+                                each=move || cabinets.get()
+                                key=|(id, _)| id.clone()
+                                children=move |(id, label)| {
+                                    view! { <option value=id.clone()>{label}</option> }
+                                }
+                            />
+                        </select>
+                    </div>
+                    <div class="d412-field">
+                        <label>"Упреждение, нед."</label>
+                        <input
+                            type="number"
+                            min="0"
+                            max="12"
+                            prop:value=move || lead_weeks.get().to_string()
+                            on:input=move |ev| {
+                                let weeks = event_target_value(&ev).parse::<u32>().unwrap_or(1).min(12);
+                                lead_weeks.set(weeks);
+                            }
+                        />
+                    </div>
+                    <button class="d412-btn" on:click=move |_| load() disabled=move || loading.get()>
+                        {move || if loading.get() { "Загрузка..." } else { "Обновить" }}
+                    </button>
+                </div>
+
+                {move || error.get().map(|message| view! {
+                    <div class="d412-state">{message}</div>
+                })}
+
+                {move || data.get().map(|response| {
+                    if response.latest_run.is_none() {
+                        return view! {
+                            <div class="d412-state">
+                                "Прогноз ещё не рассчитывался: включите регламентное задание «Аналитика — прогноз спроса»."
+                            </div>
+                        }.into_any();
+                    }
+                    let latest_run = response.latest_run.clone().unwrap_or_default();
+                    view! {
+                        {metrics_cards(&response.metrics, response.lead_weeks)}
+                        <div class="d412-chart-wrap">
+                            {forecast_chart(&response.history, &response.outlook)}
+                            <div class="d412-legend">
+                                <span><span class="d412-swatch" style="background:#2563eb"></span>"Факт"</span>
+                                <span>
+                                    <span class="d412-swatch" style="background:#f59e0b"></span>
+                                    {format!("Прогноз за {} нед.", response.lead_weeks)}
+                                </span>
+                                <span>
+                                    <span class="d412-swatch" style="background:#16a34a"></span>
+                                    {format!("Последний прогноз (расчёт от {latest_run})")}
+                                </span>
+                            </div>
+                        </div>
+                        <div class="d412-table-wrap">
+                            <table class="d412-table">
+                                <thead>
+                                    <tr>
+                                        <th>"Артикул"</th>
+                                        <th>"Товар"</th>
+                                        <th>"Метод"</th>
+                                        <th class="d412-num">{format!("Прогноз на {PRODUCT_SUMMARY_WEEKS} нед., шт.")}</th>
+                                        <th class="d412-num">"Факт за период, шт."</th>
+                                        <th class="d412-num">"Недель"</th>
+                                        <th class="d412-num">"MAE"</th>
+                                        <th class="d412-num">"WAPE"</th>
+                                        <th class="d412-num">"Смещение"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {response.products.into_iter().map(|row| view! {
+                                        <tr>
+                                            <td>{row.article}</td>
+                                            <td>{row.product_name}</td>
+                                            <td>{row.method.map(|method| method.label()).unwrap_or("—")}</td>
+                                            <td class="d412-num">{format_number(row.forecast_next_weeks, 0)}</td>
+                                            <td class="d412-num">{format_number(row.actual_qty, 0)}</td>
+                                            <td class="d412-num">{row.metrics.weeks}</td>
+                                            <td class="d412-num">{format_number(row.metrics.mae, 1)}</td>
+                                            <td class="d412-num">{format_percent_opt(row.metrics.wape, 1)}</td>
+                                            <td class="d412-num">{format_percent_opt(row.metrics.bias, 1)}</td>
+                                        </tr>
+                                    }).collect_view()}
+                                </tbody>
+                            </table>
+                        </div>
+                    }.into_any()
+                })}
+            </div>
+        </PageFrame>
+    }
+}
@@ -0,0 +1,40 @@
+use contracts::domain::a007_marketplace_product::aggregate::{
+    CrossMappingResponse, ResolveBarcodeConflictRequest, ResolveBarcodeConflictResponse,
+};
+use gloo_net::http::Request;
+
+pub async fn get_cross_mapping() -> Result<CrossMappingResponse, String> {
+    let response = Request::get("/api/a007/cross-mapping")
+        .send()
+        .await
+        .map_err(|error| format!("Request failed: {error}"))?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|error| format!("Failed to parse response: {error}"))
+}
+
+pub async fn resolve_conflict(
+    barcode: &str,
+    nomenclature_ref: &str,
+) -> Result<ResolveBarcodeConflictResponse, String> {
+    let response = Request::post("/api/a007/cross-mapping/resolve")
+        .json(&ResolveBarcodeConflictRequest {
+            barcode: barcode.to_string(),
+            nomenclature_ref: nomenclature_ref.to_string(),
+        })
+        .map_err(|error| format!("Failed to serialize request: {error}"))?
+        .send()
+        .await
+        .map_err(|error| format!("Request failed: {error}"))?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|error| format!("Failed to parse response: {error}"))
+}
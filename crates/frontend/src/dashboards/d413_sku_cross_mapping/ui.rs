@@ -0,0 +1,352 @@
+use crate::dashboards::d413_sku_cross_mapping::api;
+use crate::layout::global_context::AppGlobalContext;
+use crate::shared::components::ui::badge::Badge;
+use crate::shared::page_frame::PageFrame;
+use contracts::domain::a007_marketplace_product::aggregate::{
+    BarcodeConflictDto, CrossMappingGroupDto, CrossMappingProductDto, CrossMappingResponse,
+};
+use contracts::enums::marketplace_type::MarketplaceType;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+fn marketplace_label(code: &str) -> String {
+    MarketplaceType::from_code(code)
+        .map(|mp| mp.display_name().to_string())
+        .unwrap_or_else(|| {
+            if code.is_empty() {
+                "—".to_string()
+            } else {
+                code.to_string()
+            }
+        })
+}
+
+fn source_label(source: &str) -> String {
+    match source {
+        "1C" => "1С".to_string(),
+        "OZON" | "WB" | "YM" => format!("{source} (штрихкоды)"),
+        code => marketplace_label(code),
+    }
+}
+
+fn nomenclature_title(article: &Option<String>, name: &Option<String>, id: &str) -> String {
+    match (article.as_deref(), name.as_deref()) {
+        (Some(article), Some(name)) if !article.is_empty() => format!("{article} · {name}"),
+        (_, Some(name)) => name.to_string(),
+        _ => id.to_string(),
+    }
+}
+
+fn matches_search(group: &CrossMappingGroupDto, needle: &str) -> bool {
+    if needle.is_empty() {
+        return true;
+    }
+    let hit = |value: &str| value.to_lowercase().contains(needle);
+    group.nomenclature_article.as_deref().is_some_and(hit)
+        || group.nomenclature_name.as_deref().is_some_and(hit)
+        || group.products.iter().any(|product| {
+            hit(&product.article)
+                || hit(&product.marketplace_sku)
+                || product.barcode.as_deref().is_some_and(hit)
+        })
+}
+
+#[component]
+pub fn SkuCrossMappingDashboard() -> impl IntoView {
+    let tabs = expect_context::<AppGlobalContext>();
+    let data = RwSignal::new(None::<CrossMappingResponse>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+    let notice = RwSignal::new(None::<String>);
+    let show_conflicts = RwSignal::new(true);
+    let only_cross = RwSignal::new(true);
+    let search = RwSignal::new(String::new());
+
+    let load = move || {
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::get_cross_mapping().await {
+                Ok(response) => data.set(Some(response)),
+                Err(message) => error.set(Some(message)),
+            }
+            loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| load());
+
+    let resolve = move |barcode: String, nomenclature_ref: String| {
+        notice.set(None);
+        spawn_local(async move {
+            match api::resolve_conflict(&barcode, &nomenclature_ref).await {
+                Ok(result) => {
+                    notice.set(Some(format!(
+                        "Штрихкод {barcode}: перепривязано товаров — {}, штрихкодов p901 — {}",
+                        result.products_updated, result.barcodes_updated
+                    )));
+                    load();
+                }
+                Err(message) => error.set(Some(message)),
+            }
+        });
+    };
+
+    let product_link = move |product: CrossMappingProductDto| {
+        let label = if product.article.is_empty() {
+            product.marketplace_sku.clone()
+        } else {
+            product.article.clone()
+        };
+        let tab_key = format!("a007_marketplace_product_details_{}", product.id);
+        let tab_title = format!("Товар МП {label}");
+        view! {
+            <div class="d413-product">
+                <Badge variant="neutral".to_string()>{marketplace_label(&product.marketplace_code)}</Badge>
+                <button
+                    class="d413-link"
+                    title=product.description.clone()
+                    on:click=move |_| tabs.open_tab(&tab_key, &tab_title)
+                >
+                    {label}
+                </button>
+                <span class="d413-muted">
+                    {product.barcode.clone().unwrap_or_default()}
+                </span>
+            </div>
+        }
+    };
+
+    let nomenclature_link = move |id: String, title: String| {
+        let tab_key = format!("a004_nomenclature_details_{id}");
+        let tab_title = format!("Номенклатура {title}");
+        view! {
+            <button class="d413-link" on:click=move |_| tabs.open_tab(&tab_key, &tab_title)>
+                {title.clone()}
+            </button>
+        }
+    };
+
+    let render_conflict = move |conflict: BarcodeConflictDto| {
+        let barcode = conflict.barcode.clone();
+        let claims = conflict
+            .claims
+            .into_iter()
+            .map(|claim| {
+                let title = nomenclature_title(
+                    &claim.nomenclature_article,
+                    &claim.nomenclature_name,
+                    &claim.nomenclature_ref,
+                );
+                let sources = claim
+                    .sources
+                    .iter()
+                    .map(|source| source_label(source))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let barcode = barcode.clone();
+                let nomenclature_ref = claim.nomenclature_ref.clone();
+                view! {
+                    <div class="d413-claim">
+                        {nomenclature_link(claim.nomenclature_ref.clone(), title)}
+                        <span class="d413-muted">{sources}</span>
+                        <button
+                            class="d413-btn d413-btn--small"
+                            title="Привязать все товары и маркетплейсные штрихкоды к этой номенклатуре"
+                            on:click=move |_| resolve(barcode.clone(), nomenclature_ref.clone())
+                        >
+                            "Выбрать"
+                        </button>
+                    </div>
+                }
+            })
+            .collect_view();
+        view! {
+            <tr>
+                <td class="d413-mono">{conflict.barcode.clone()}</td>
+                <td>{claims}</td>
+                <td>
+                    {conflict.products.into_iter().map(product_link).collect_view()}
+                </td>
+            </tr>
+        }
+    };
+
+    let render_group = move |group: CrossMappingGroupDto| {
+        let title = nomenclature_title(
+            &group.nomenclature_article,
+            &group.nomenclature_name,
+            &group.nomenclature_ref,
+        );
+        let marketplaces = group
+            .marketplace_codes
+            .iter()
+            .map(|code| marketplace_label(code))
+            .collect::<Vec<_>>()
+            .join(", ");
+        view! {
+            <tr>
+                <td>{nomenclature_link(group.nomenclature_ref.clone(), title)}</td>
+                <td>{marketplaces}</td>
+                <td>{group.products.into_iter().map(product_link).collect_view()}</td>
+            </tr>
+        }
+    };
+
+    view! {
+        <PageFrame page_id="d413_sku_cross_mapping--dashboard" category="dashboard" class="page--wide">
+            <style>
+                ".d413-shell{display:flex;flex-direction:column;gap:12px;height:100%}
+                .d413-toolbar{display:flex;gap:10px;align-items:end;flex-wrap:wrap;padding:10px 0}
+                .d413-field{display:flex;flex-direction:column;gap:4px;min-width:220px}
+                .d413-field label{font-size:12px;color:var(--color-text-secondary)}
+                .d413-field input{height:32px;border:1px solid var(--color-border);border-radius:6px;padding:0 8px;background:var(--color-surface);color:var(--color-text-primary)}
+                .d413-check{display:flex;gap:6px;align-items:center;height:32px;font-size:13px}
+                .d413-btn{height:32px;border:1px solid var(--color-border);border-radius:6px;background:var(--color-surface);color:var(--color-text-primary);padding:0 12px;cursor:pointer}
+                .d413-btn--active{border-color:var(--color-brand,#2563eb);color:var(--color-brand,#2563eb)}
+                .d413-btn--small{height:26px;padding:0 8px;font-size:12px}
+                .d413-summary{font-size:13px;color:var(--color-text-secondary)}
+                .d413-summary strong{color:var(--color-text-primary)}
+                .d413-table-wrap{overflow:auto;border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface)}
+                .d413-table{width:100%;border-collapse:collapse;font-size:13px}
+                .d413-table th{position:sticky;top:0;background:var(--color-surface);z-index:1;text-align:left;border-bottom:1px solid var(--color-border);padding:8px;color:var(--color-text-secondary);font-weight:600}
+                .d413-table td{border-bottom:1px solid var(--color-border-light,var(--color-border));padding:7px 8px;vertical-align:top}
+                .d413-claim,.d413-product{display:flex;gap:8px;align-items:center;padding:2px 0}
+                .d413-mono{font-family:var(--font-mono,monospace)}
+                .d413-muted{font-size:11px;color:var(--color-text-secondary)}
+                .d413-link{border:0;background:transparent;color:var(--color-brand,#2563eb);cursor:pointer;font:inherit;padding:0;text-align:left}
+                .d413-link:hover{text-decoration:underline}
+                .d413-state{padding:18px;color:var(--color-text-secondary)}"
+            </style>
+            <div class="d413-shell">
+                <div>
+                    <h1 style="margin:0;font-size:20px;">"Сопоставление SKU между маркетплейсами"</h1>
+                    <div style="color:var(--color-text-secondary);font-size:13px;">
+                        "Товары разных маркетплейсов, привязанные к одной номенклатуре, и штрихкоды, которые источники относят к разным номенклатурам"
+                    </div>
+                </div>
+
+                <div class="d413-toolbar">
+                    <button
+                        class="d413-btn"
+                        class:d413-btn--active=move || show_conflicts.get()
+                        on:click=move |_| show_conflicts.set(true)
+                    >
+                        {move || {
+                            let count = data.with(|d| d.as_ref().map(|d| d.conflicts.len()).unwrap_or(0));
+                            format!("Конфликты ({count})")
+                        }}
+                    </button>
+                    <button
+                        class="d413-btn"
+                        class:d413-btn--active=move || !show_conflicts.get()
+                        on:click=move |_| show_conflicts.set(false)
+                    >
+                        "Группы"
+                    </button>
+                    <div class="d413-field">
+                        <label>"Поиск"</label>
+                        <input
+                            type="text"
+                            placeholder="Артикул, SKU, штрихкод, номенклатура"
+                            prop:value=move || search.get()
+                            on:input=move |ev| search.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <Show when=move || !show_conflicts.get()>
+                        <label class="d413-check">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || only_cross.get()
+                                on:change=move |ev| only_cross.set(event_target_checked(&ev))
+                            />
+                            "Только на нескольких маркетплейсах"
+                        </label>
+                    </Show>
+                    <button class="d413-btn" on:click=move |_| load() disabled=move || loading.get()>
+                        {move || if loading.get() { "Загрузка..." } else { "Обновить" }}
+                    </button>
+                </div>
+
+                {move || error.get().map(|message| view! { <div class="d413-state">{message}</div> })}
+                {move || notice.get().map(|message| view! { <div class="d413-summary">{message}</div> })}
+
+                {move || data.get().map(|response| {
+                    let cross = response.groups.iter().filter(|g| g.is_cross_marketplace()).count();
+                    view! {
+                        <div class="d413-summary">
+                            "Номенклатур с товарами: " <strong>{response.groups.len()}</strong>
+                            " · на нескольких маркетплейсах: " <strong>{cross}</strong>
+                            " · товаров без номенклатуры: " <strong>{response.unmapped_count}</strong>
+                        </div>
+                    }
+                })}
+
+                <div class="d413-table-wrap">
+                    <table class="d413-table">
+                        <thead>
+                            <tr>
+                                {move || if show_conflicts.get() {
+                                    view! {
+                                        <th>"Штрихкод"</th>
+                                        <th>"Номенклатуры и источники"</th>
+                                        <th>"Товары маркетплейсов"</th>
+                                    }.into_any()
+                                } else {
+                                    view! {
+                                        <th>"Номенклатура"</th>
+                                        <th>"Маркетплейсы"</th>
+                                        <th>"Товары маркетплейсов"</th>
+                                    }.into_any()
+                                }}
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {move || {
+                                let needle = search.get().trim().to_lowercase();
+                                let Some(response) = data.get() else {
+                                    return view! {
+                                        <tr><td class="d413-state" colspan="3">"Загрузка..."</td></tr>
+                                    }.into_any();
+                                };
+                                if show_conflicts.get() {
+                                    let rows: Vec<BarcodeConflictDto> = response
+                                        .conflicts
+                                        .into_iter()
+                                        .filter(|conflict| {
+                                            needle.is_empty()
+                                                || conflict.barcode.contains(&needle)
+                                                || conflict.claims.iter().any(|claim| {
+                                                    claim.nomenclature_article.as_deref().is_some_and(|a| a.to_lowercase().contains(&needle))
+                                                })
+                                        })
+                                        .collect();
+                                    if rows.is_empty() {
+                                        return view! {
+                                            <tr><td class="d413-state" colspan="3">"Конфликтующих штрихкодов нет."</td></tr>
+                                        }.into_any();
+                                    }
+                                    rows.into_iter().map(render_conflict).collect_view().into_any()
+                                } else {
+                                    let cross_only = only_cross.get();
+                                    let rows: Vec<CrossMappingGroupDto> = response
+                                        .groups
+                                        .into_iter()
+                                        .filter(|group| !cross_only || group.is_cross_marketplace())
+                                        .filter(|group| matches_search(group, &needle))
+                                        .collect();
+                                    if rows.is_empty() {
+                                        return view! {
+                                            <tr><td class="d413-state" colspan="3">"Групп не найдено."</td></tr>
+                                        }.into_any();
+                                    }
+                                    rows.into_iter().map(render_group).collect_view().into_any()
+                                }
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+            </div>
+        </PageFrame>
+    }
+}
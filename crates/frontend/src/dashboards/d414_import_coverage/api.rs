@@ -0,0 +1,34 @@
+use contracts::dashboards::d414_import_coverage::ImportCoverageResponse;
+use gloo_net::http::Request;
+
+pub async fn get_import_coverage(
+    date_from: &str,
+    date_to: &str,
+    connection_mp_ref: &str,
+    problems_only: bool,
+) -> Result<ImportCoverageResponse, String> {
+    let mut params = vec![
+        format!("date_from={}", urlencoding::encode(date_from.trim())),
+        format!("date_to={}", urlencoding::encode(date_to.trim())),
+        format!("problems_only={problems_only}"),
+    ];
+    if !connection_mp_ref.trim().is_empty() {
+        params.push(format!(
+            "connection_mp_ref={}",
+            urlencoding::encode(connection_mp_ref.trim())
+        ));
+    }
+
+    let url = format!("/api/dashboards/import-coverage?{}", params.join("&"));
+    let response = Request::get(&url)
+        .send()
+        .await
+        .map_err(|error| format!("Request failed: {error}"))?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|error| format!("Failed to parse response: {error}"))
+}
@@ -0,0 +1,277 @@
+use crate::dashboards::d414_import_coverage::api;
+use crate::shared::api_utils::api_base;
+use crate::shared::money_format::format_number;
+use crate::shared::page_frame::PageFrame;
+use chrono::{Duration, Utc};
+use contracts::dashboards::d414_import_coverage::{
+    CoverageStatus, ImportCoverageResponse, ImportCoverageRow,
+};
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::common::AggregateId;
+use gloo_net::http::Request;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Период по умолчанию — последние 14 дней.
+fn default_date_from() -> String {
+    (Utc::now().date_naive() - Duration::days(14))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn today() -> String {
+    Utc::now().date_naive().format("%Y-%m-%d").to_string()
+}
+
+fn status_class(status: CoverageStatus) -> &'static str {
+    match status {
+        CoverageStatus::Ok => "d414-status d414-status--ok",
+        CoverageStatus::Missing | CoverageStatus::Partial => "d414-status d414-status--gap",
+        CoverageStatus::Surplus | CoverageStatus::AmountMismatch => {
+            "d414-status d414-status--mismatch"
+        }
+        CoverageStatus::NotChecked => "d414-status",
+    }
+}
+
+fn count_opt(value: Option<i64>) -> String {
+    value
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "—".to_string())
+}
+
+fn amount_opt(value: Option<f64>) -> String {
+    value
+        .map(|v| format_number(v, 2))
+        .unwrap_or_else(|| "—".to_string())
+}
+
+fn coverage_row(row: ImportCoverageRow) -> impl IntoView {
+    let row_class = if row.status.is_gap() {
+        "d414-row--gap"
+    } else if row.status.is_problem() {
+        "d414-row--mismatch"
+    } else {
+        ""
+    };
+    let count_delta = row
+        .claimed_count
+        .map(|claimed| row.imported_count - claimed)
+        .filter(|delta| *delta != 0)
+        .map(|delta| format!("{delta:+}"))
+        .unwrap_or_default();
+    let amount_delta = row
+        .claimed_amount
+        .map(|claimed| row.imported_amount - claimed)
+        .filter(|delta| delta.abs() > f64::EPSILON)
+        .map(|delta| format_number(delta, 2))
+        .unwrap_or_default();
+    view! {
+        <tr class=row_class>
+            <td>{row.day}</td>
+            <td>{row.connection_name}</td>
+            <td>{row.source.label()}</td>
+            <td class="d414-num">{count_opt(row.claimed_count)}</td>
+            <td class="d414-num">{row.imported_count}</td>
+            <td class="d414-num">{count_delta}</td>
+            <td class="d414-num">{amount_opt(row.claimed_amount)}</td>
+            <td class="d414-num">{format_number(row.imported_amount, 2)}</td>
+            <td class="d414-num">{amount_delta}</td>
+            <td><span class=status_class(row.status)>{row.status.label()}</span></td>
+        </tr>
+    }
+}
+
+#[component]
+pub fn ImportCoverageDashboard() -> impl IntoView {
+    let date_from = RwSignal::new(default_date_from());
+    let date_to = RwSignal::new(today());
+    let connection_mp_ref = RwSignal::new(String::new());
+    let problems_only = RwSignal::new(false);
+    let cabinets = RwSignal::new(Vec::<(String, String)>::new());
+    let data = RwSignal::new(None::<ImportCoverageResponse>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    spawn_local(async move {
+        let url = format!("{}/api/connection_mp", api_base());
+        let Ok(resp) = Request::get(&url).send().await else {
+            return;
+        };
+        if !resp.ok() {
+            return;
+        }
+        if let Ok(data) = resp.json::<Vec<ConnectionMP>>().await {
+            let mut opts: Vec<(String, String)> = data
+                .into_iter()
+                .map(|conn| {
+                    let label = if conn.base.description.trim().is_empty() {
+                        conn.base.code.clone()
+                    } else {
+                        conn.base.description.clone()
+                    };
+                    (conn.base.id.as_string(), label)
+                })
+                .collect();
+            opts.sort_by(|a, b| a.1.cmp(&b.1));
+            cabinets.set(opts);
+        }
+    });
+
+    let load = move || {
+        let df = date_from.get_untracked();
+        let dt = date_to.get_untracked();
+        let conn = connection_mp_ref.get_untracked();
+        let only_problems = problems_only.get_untracked();
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::get_import_coverage(&df, &dt, &conn, only_problems).await {
+                Ok(response) => data.set(Some(response)),
+                Err(message) => error.set(Some(message)),
+            }
+            loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| load());
+
+    view! {
+        <PageFrame page_id="d414_import_coverage--dashboard" category="dashboard" class="page--wide">
+            <style>
+                ".d414-shell{display:flex;flex-direction:column;gap:12px;height:100%}
+                .d414-toolbar{display:flex;gap:10px;align-items:end;flex-wrap:wrap;padding:10px 0}
+                .d414-field{display:flex;flex-direction:column;gap:4px;min-width:140px}
+                .d414-field label{font-size:12px;color:var(--color-text-secondary)}
+                .d414-field input,.d414-field select{height:32px;border:1px solid var(--color-border);border-radius:6px;padding:0 8px;background:var(--color-surface);color:var(--color-text-primary)}
+                .d414-check{display:flex;gap:6px;align-items:center;height:32px;font-size:13px}
+                .d414-btn{height:32px;border:1px solid var(--color-border);border-radius:6px;background:var(--color-surface);color:var(--color-text-primary);padding:0 12px;cursor:pointer}
+                .d414-cards{display:grid;grid-template-columns:repeat(auto-fit,minmax(170px,1fr));gap:10px}
+                .d414-card{border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface);padding:10px 12px}
+                .d414-card-label{font-size:12px;color:var(--color-text-secondary)}
+                .d414-card-value{font-size:20px;font-weight:600;font-variant-numeric:tabular-nums}
+                .d414-table-wrap{overflow:auto;border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface)}
+                .d414-table{width:100%;border-collapse:collapse;font-size:12px}
+                .d414-table th{position:sticky;top:0;background:var(--color-surface);z-index:1;border-bottom:1px solid var(--color-border);padding:6px 8px;color:var(--color-text-secondary);font-weight:600;white-space:nowrap;text-align:left}
+                .d414-table td{border-bottom:1px solid var(--color-border-light,var(--color-border));padding:6px 8px;white-space:nowrap}
+                .d414-num{text-align:right !important;font-variant-numeric:tabular-nums}
+                .d414-row--gap td{background:rgba(220,38,38,0.08)}
+                .d414-row--mismatch td{background:rgba(245,158,11,0.08)}
+                .d414-status{display:inline-block;padding:1px 8px;border-radius:10px;font-size:11px;background:var(--color-border-light,var(--color-border));color:var(--color-text-secondary)}
+                .d414-status--ok{background:rgba(22,163,74,0.12);color:#15803d}
+                .d414-status--gap{background:rgba(220,38,38,0.12);color:#b91c1c}
+                .d414-status--mismatch{background:rgba(245,158,11,0.15);color:#b45309}
+                .d414-state{padding:18px;color:var(--color-text-secondary)}"
+            </style>
+            <div class="d414-shell">
+                <div>
+                    <h1 style="margin:0;font-size:20px;">"Покрытие импорта"</h1>
+                    <div style="color:var(--color-text-secondary);font-size:13px;">
+                        "Сколько документов и на какую сумму заявил маркетплейс за день против загруженного к нам — пропущенные дни видны до закрытия месяца"
+                    </div>
+                </div>
+
+                <div class="d414-toolbar">
+                    <div class="d414-field">
+                        <label>"С"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_from.get()
+                            on:input=move |ev| date_from.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d414-field">
+                        <label>"по"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_to.get()
+                            on:input=move |ev| date_to.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d414-field">
+                        <label>"Кабинет"</label>
+                        <select
+                            prop:value=move || connection_mp_ref.get()
+                            on:change=move |ev| connection_mp_ref.set(event_target_value(&ev))
+                        >
+                            <option value="">"Все кабинеты"</option>
+                            <For
+                                each=move || cabinets.get()
+                                key=|(id, _)| id.clone()
+                                children=move |(id, label)| {
+                                    view! { <option value=id.clone()>{label}</option> }
+                                }
+                            />
+                        </select>
+                    </div>
+                    <label class="d414-check">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || problems_only.get()
+                            on:change=move |ev| problems_only.set(event_target_checked(&ev))
+                        />
+                        "Только расхождения"
+                    </label>
+                    <button class="d414-btn" on:click=move |_| load() disabled=move || loading.get()>
+                        {move || if loading.get() { "Загрузка..." } else { "Обновить" }}
+                    </button>
+                </div>
+
+                {move || error.get().map(|message| view! {
+                    <div class="d414-state">{message}</div>
+                })}
+
+                {move || data.get().map(|response| {
+                    let last_checked = response
+                        .last_checked_at
+                        .clone()
+                        .map(|value| value.chars().take(16).collect::<String>().replace('T', " "))
+                        .unwrap_or_else(|| "—".to_string());
+                    let empty = response.rows.is_empty();
+                    view! {
+                        <div class="d414-cards">
+                            <div class="d414-card">
+                                <div class="d414-card-label">"Дней с пропусками"</div>
+                                <div class="d414-card-value">{response.gap_days}</div>
+                            </div>
+                            <div class="d414-card">
+                                <div class="d414-card-label">"Строк с расхождениями"</div>
+                                <div class="d414-card-value">{response.problem_rows}</div>
+                            </div>
+                            <div class="d414-card">
+                                <div class="d414-card-label">"Последняя сверка (UTC)"</div>
+                                <div class="d414-card-value">{last_checked}</div>
+                            </div>
+                        </div>
+                        <Show when=move || empty>
+                            <div class="d414-state">
+                                "Нет данных за период. Сводки маркетплейсов собирает регламентное задание «Контроль — покрытие импорта по дням»."
+                            </div>
+                        </Show>
+                        <div class="d414-table-wrap">
+                            <table class="d414-table">
+                                <thead>
+                                    <tr>
+                                        <th>"День"</th>
+                                        <th>"Кабинет"</th>
+                                        <th>"Источник"</th>
+                                        <th class="d414-num">"Заявлено, шт."</th>
+                                        <th class="d414-num">"Загружено, шт."</th>
+                                        <th class="d414-num">"Δ, шт."</th>
+                                        <th class="d414-num">"Заявлено, ₽"</th>
+                                        <th class="d414-num">"Загружено, ₽"</th>
+                                        <th class="d414-num">"Δ, ₽"</th>
+                                        <th>"Статус"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {response.rows.into_iter().map(coverage_row).collect_view()}
+                                </tbody>
+                            </table>
+                        </div>
+                    }
+                })}
+            </div>
+        </PageFrame>
+    }
+}
@@ -0,0 +1,23 @@
+use contracts::dashboards::d415_sku_pnl::SkuPnlResponse;
+
+use crate::shared::api_client;
+
+pub async fn get_sku_pnl(
+    date_from: &str,
+    date_to: &str,
+    connection_mp_ref: &str,
+) -> Result<SkuPnlResponse, String> {
+    let mut params = vec![
+        format!("date_from={}", urlencoding::encode(date_from.trim())),
+        format!("date_to={}", urlencoding::encode(date_to.trim())),
+    ];
+    if !connection_mp_ref.trim().is_empty() {
+        params.push(format!(
+            "connection_mp_ref={}",
+            urlencoding::encode(connection_mp_ref.trim())
+        ));
+    }
+    api_client::get_json(&format!("/api/dashboards/sku-pnl?{}", params.join("&")))
+        .await
+        .map_err(|error| error.to_string())
+}
@@ -0,0 +1,359 @@
+use crate::dashboards::d415_sku_pnl::api;
+use crate::shared::api_utils::api_base;
+use crate::shared::bi_card::points_to_svg_path;
+use crate::shared::money_format::{format_money, format_percent};
+use crate::shared::page_frame::PageFrame;
+use chrono::{Duration, Utc};
+use contracts::dashboards::d415_sku_pnl::{
+    delta_percent, SkuPnlMetrics, SkuPnlResponse, SkuPnlRow,
+};
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::common::AggregateId;
+use gloo_net::http::Request;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::cmp::Ordering;
+
+/// Период по умолчанию — последние 30 дней.
+fn default_date_from() -> String {
+    (Utc::now().date_naive() - Duration::days(29))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn today() -> String {
+    Utc::now().date_naive().format("%Y-%m-%d").to_string()
+}
+
+/// Показатель по коду колонки (см. заголовки таблицы).
+fn metric(metrics: &SkuPnlMetrics, field: &str) -> f64 {
+    match field {
+        "revenue" => metrics.revenue,
+        "commission" => metrics.commission,
+        "acquiring" => metrics.acquiring,
+        "coinvest" => metrics.coinvest,
+        _ => metrics.payout,
+    }
+}
+
+fn cmp_opt_f64(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn sort_rows(rows: &mut [SkuPnlRow], field: &str, ascending: bool) {
+    rows.sort_by(|a, b| {
+        let ord = match field {
+            "article" => a.article.cmp(&b.article),
+            "description" => a.description.cmp(&b.description),
+            "payout_delta" => cmp_opt_f64(
+                delta_percent(a.current.payout, a.previous.payout),
+                delta_percent(b.current.payout, b.previous.payout),
+            ),
+            field => metric(&a.current, field).total_cmp(&metric(&b.current, field)),
+        };
+        if ascending {
+            ord
+        } else {
+            ord.reverse()
+        }
+    });
+}
+
+fn delta_view(current: f64, previous: f64) -> impl IntoView {
+    let (text, class) = match delta_percent(current, previous) {
+        Some(delta) => (
+            format!(
+                "{}{}",
+                if delta > 0.0 { "+" } else { "" },
+                format_percent(delta, 1)
+            ),
+            if delta >= 0.0 {
+                "d415-delta d415-delta--up"
+            } else {
+                "d415-delta d415-delta--down"
+            },
+        ),
+        None => ("—".to_string(), "d415-delta"),
+    };
+    view! { <div class=class title=format!("Пред. период: {}", format_money(previous))>{text}</div> }
+}
+
+fn metric_cell(row: &SkuPnlRow, field: &str) -> impl IntoView {
+    let current = metric(&row.current, field);
+    let previous = metric(&row.previous, field);
+    view! {
+        <td class="d415-num">
+            <div>{format_money(current)}</div>
+            {delta_view(current, previous)}
+        </td>
+    }
+}
+
+fn trend_view(trend: &[f64]) -> impl IntoView {
+    let (line_d, fill_d) = points_to_svg_path(trend);
+    let title = trend
+        .iter()
+        .enumerate()
+        .map(|(i, value)| format!("{}: {}", i + 1, format_money(*value)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    view! {
+        <svg viewBox="0 0 100 30" preserveAspectRatio="none" class="d415-spark">
+            <title>{title}</title>
+            <path d=fill_d fill="var(--color-primary)" opacity="0.15" stroke="none" />
+            <path d=line_d fill="none" stroke="var(--color-primary)" stroke-width="1.5" vector-effect="non-scaling-stroke" />
+        </svg>
+    }
+}
+
+fn pnl_row(row: SkuPnlRow) -> impl IntoView {
+    let title = row
+        .nomenclature_ref
+        .clone()
+        .unwrap_or_else(|| "Нет связи с номенклатурой".to_string());
+    view! {
+        <tr>
+            <td title=title>{row.article.clone()}</td>
+            <td class="d415-name">{row.description.clone()}</td>
+            {metric_cell(&row, "revenue")}
+            {metric_cell(&row, "commission")}
+            {metric_cell(&row, "acquiring")}
+            {metric_cell(&row, "coinvest")}
+            {metric_cell(&row, "payout")}
+            <td>{trend_view(&row.trend)}</td>
+        </tr>
+    }
+}
+
+fn total_card(label: &'static str, current: f64, previous: f64) -> impl IntoView {
+    view! {
+        <div class="d415-card">
+            <div class="d415-card-label">{label}</div>
+            <div class="d415-card-value">{format_money(current)}</div>
+            {delta_view(current, previous)}
+        </div>
+    }
+}
+
+fn sort_th(
+    label: &'static str,
+    field: &'static str,
+    numeric: bool,
+    sort_field: RwSignal<String>,
+    sort_asc: RwSignal<bool>,
+) -> impl IntoView {
+    let class = if numeric { "d415-num" } else { "" };
+    let indicator = move || {
+        if sort_field.get() == field {
+            if sort_asc.get() {
+                "▲"
+            } else {
+                "▼"
+            }
+        } else {
+            "↕"
+        }
+    };
+    view! {
+        <th
+            class=class
+            on:click=move |_| {
+                if sort_field.get() == field {
+                    sort_asc.update(|v| *v = !*v);
+                } else {
+                    sort_field.set(field.to_string());
+                    sort_asc.set(!numeric);
+                }
+            }
+        >
+            {label}
+            <span class="d415-sort">{indicator}</span>
+        </th>
+    }
+}
+
+#[component]
+pub fn SkuPnlDashboard() -> impl IntoView {
+    let date_from = RwSignal::new(default_date_from());
+    let date_to = RwSignal::new(today());
+    let connection_mp_ref = RwSignal::new(String::new());
+    let cabinets = RwSignal::new(Vec::<(String, String)>::new());
+    let data = RwSignal::new(None::<SkuPnlResponse>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+    let sort_field = RwSignal::new("payout".to_string());
+    let sort_asc = RwSignal::new(false);
+
+    spawn_local(async move {
+        let url = format!("{}/api/connection_mp", api_base());
+        let Ok(resp) = Request::get(&url).send().await else {
+            return;
+        };
+        if !resp.ok() {
+            return;
+        }
+        if let Ok(data) = resp.json::<Vec<ConnectionMP>>().await {
+            let mut opts: Vec<(String, String)> = data
+                .into_iter()
+                .map(|conn| {
+                    let label = if conn.base.description.trim().is_empty() {
+                        conn.base.code.clone()
+                    } else {
+                        conn.base.description.clone()
+                    };
+                    (conn.base.id.as_string(), label)
+                })
+                .collect();
+            opts.sort_by(|a, b| a.1.cmp(&b.1));
+            cabinets.set(opts);
+        }
+    });
+
+    let load = move || {
+        let df = date_from.get_untracked();
+        let dt = date_to.get_untracked();
+        let conn = connection_mp_ref.get_untracked();
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::get_sku_pnl(&df, &dt, &conn).await {
+                Ok(response) => data.set(Some(response)),
+                Err(message) => error.set(Some(message)),
+            }
+            loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| load());
+
+    view! {
+        <PageFrame page_id="d415_sku_pnl--dashboard" category="dashboard" class="page--wide">
+            <style>
+                ".d415-shell{display:flex;flex-direction:column;gap:12px;height:100%}
+                .d415-toolbar{display:flex;gap:10px;align-items:end;flex-wrap:wrap;padding:10px 0}
+                .d415-field{display:flex;flex-direction:column;gap:4px;min-width:140px}
+                .d415-field label{font-size:12px;color:var(--color-text-secondary)}
+                .d415-field input,.d415-field select{height:32px;border:1px solid var(--color-border);border-radius:6px;padding:0 8px;background:var(--color-surface);color:var(--color-text-primary)}
+                .d415-btn{height:32px;border:1px solid var(--color-border);border-radius:6px;background:var(--color-surface);color:var(--color-text-primary);padding:0 12px;cursor:pointer}
+                .d415-cards{display:grid;grid-template-columns:repeat(auto-fit,minmax(170px,1fr));gap:10px}
+                .d415-card{border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface);padding:10px 12px}
+                .d415-card-label{font-size:12px;color:var(--color-text-secondary)}
+                .d415-card-value{font-size:20px;font-weight:600;font-variant-numeric:tabular-nums}
+                .d415-table-wrap{overflow:auto;border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface)}
+                .d415-table{width:100%;border-collapse:collapse;font-size:12px}
+                .d415-table th{position:sticky;top:0;background:var(--color-surface);z-index:1;border-bottom:1px solid var(--color-border);padding:6px 8px;color:var(--color-text-secondary);font-weight:600;white-space:nowrap;text-align:left;cursor:pointer;user-select:none}
+                .d415-table td{border-bottom:1px solid var(--color-border-light,var(--color-border));padding:6px 8px;white-space:nowrap;vertical-align:top}
+                .d415-num{text-align:right !important;font-variant-numeric:tabular-nums}
+                .d415-name{max-width:320px;overflow:hidden;text-overflow:ellipsis}
+                .d415-sort{margin-left:4px;opacity:0.6}
+                .d415-delta{font-size:11px;color:var(--color-text-secondary)}
+                .d415-delta--up{color:#15803d}
+                .d415-delta--down{color:#b91c1c}
+                .d415-spark{width:120px;height:24px;display:block}
+                .d415-state{padding:18px;color:var(--color-text-secondary)}"
+            </style>
+            <div class="d415-shell">
+                <div>
+                    <h1 style="margin:0;font-size:20px;">"P&L по SKU"</h1>
+                    <div style="color:var(--color-text-secondary);font-size:13px;">
+                        "Выручка, комиссия, эквайринг, соинвест и выплата продавцу по номенклатуре из продаж p904; изменение — к предыдущему периоду той же длины, тренд — выплата по отрезкам периода"
+                    </div>
+                </div>
+
+                <div class="d415-toolbar">
+                    <div class="d415-field">
+                        <label>"С"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_from.get()
+                            on:input=move |ev| date_from.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d415-field">
+                        <label>"по"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_to.get()
+                            on:input=move |ev| date_to.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="d415-field">
+                        <label>"Кабинет"</label>
+                        <select
+                            prop:value=move || connection_mp_ref.get()
+                            on:change=move |ev| connection_mp_ref.set(event_target_value(&ev))
+                        >
+                            <option value="">"Все кабинеты"</option>
+                            <For
+                                each=move || cabinets.get()
+                                key=|(id, _)| id.clone()
+                                children=move |(id, label)| {
+                                    view! { <option value=id.clone()>{label}</option> }
+                                }
+                            />
+                        </select>
+                    </div>
+                    <button class="d415-btn" on:click=move |_| load() disabled=move || loading.get()>
+                        {move || if loading.get() { "Загрузка..." } else { "Обновить" }}
+                    </button>
+                </div>
+
+                {move || error.get().map(|message| view! {
+                    <div class="d415-state">{message}</div>
+                })}
+
+                {move || data.get().map(|response| {
+                    let totals = response.totals;
+                    let previous = response.previous_totals;
+                    let previous_label = format!(
+                        "Сравнение с {} — {}",
+                        response.previous_from, response.previous_to
+                    );
+                    let empty = response.rows.is_empty();
+                    view! {
+                        <div style="color:var(--color-text-secondary);font-size:12px;">{previous_label}</div>
+                        <div class="d415-cards">
+                            {total_card("Выручка", totals.revenue, previous.revenue)}
+                            {total_card("Комиссия МП", totals.commission, previous.commission)}
+                            {total_card("Эквайринг", totals.acquiring, previous.acquiring)}
+                            {total_card("Соинвест МП", totals.coinvest, previous.coinvest)}
+                            {total_card("Выплата продавцу", totals.payout, previous.payout)}
+                        </div>
+                        <Show when=move || empty>
+                            <div class="d415-state">"Нет продаж за период."</div>
+                        </Show>
+                    }
+                })}
+
+                <div class="d415-table-wrap">
+                    <table class="d415-table">
+                        <thead>
+                            <tr>
+                                {sort_th("Артикул", "article", false, sort_field, sort_asc)}
+                                {sort_th("Номенклатура", "description", false, sort_field, sort_asc)}
+                                {sort_th("Выручка, ₽", "revenue", true, sort_field, sort_asc)}
+                                {sort_th("Комиссия, ₽", "commission", true, sort_field, sort_asc)}
+                                {sort_th("Эквайринг, ₽", "acquiring", true, sort_field, sort_asc)}
+                                {sort_th("Соинвест, ₽", "coinvest", true, sort_field, sort_asc)}
+                                {sort_th("Выплата, ₽", "payout", true, sort_field, sort_asc)}
+                                {sort_th("Тренд выплаты (Δ %)", "payout_delta", false, sort_field, sort_asc)}
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {move || {
+                                let mut rows = data.get().map(|response| response.rows).unwrap_or_default();
+                                sort_rows(&mut rows, &sort_field.get(), sort_asc.get());
+                                rows.into_iter().map(pnl_row).collect_view()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+            </div>
+        </PageFrame>
+    }
+}
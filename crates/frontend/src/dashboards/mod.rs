@@ -5,6 +5,15 @@ pub mod d403_ym_order_flow;
 pub mod d404_wb_advert_report;
 pub mod d405_metadata_dashboard;
 pub mod d406_wb_sales_funnel;
+pub mod d407_wb_supply_acceptance;
+pub mod d408_pnl_statement;
+pub mod d409_margin_scenario;
+pub mod d410_sku_launch_cohorts;
+pub mod d411_sales_anomalies;
+pub mod d412_demand_forecast;
+pub mod d413_sku_cross_mapping;
+pub mod d414_import_coverage;
+pub mod d415_sku_pnl;
 
 pub use d400_monthly_summary::ui::MonthlySummaryDashboard;
 pub use d401_wb_finance::ui::D401WbFinanceDashboard;
@@ -13,3 +22,12 @@ pub use d403_ym_order_flow::ui::YmOrderFlowDashboard;
 pub use d404_wb_advert_report::ui::WbAdvertReportDashboard;
 pub use d405_metadata_dashboard::ui::MetadataDashboard;
 pub use d406_wb_sales_funnel::ui::WbSalesFunnelDashboard;
+pub use d407_wb_supply_acceptance::ui::WbSupplyAcceptanceDashboard;
+pub use d408_pnl_statement::ui::PnlStatementDashboard;
+pub use d409_margin_scenario::ui::MarginScenarioDashboard;
+pub use d410_sku_launch_cohorts::ui::SkuLaunchCohortsDashboard;
+pub use d411_sales_anomalies::ui::SalesAnomaliesDashboard;
+pub use d412_demand_forecast::ui::DemandForecastDashboard;
+pub use d413_sku_cross_mapping::ui::SkuCrossMappingDashboard;
+pub use d414_import_coverage::ui::ImportCoverageDashboard;
+pub use d415_sku_pnl::ui::SkuPnlDashboard;
@@ -1,5 +1,6 @@
 use super::view_model::OrganizationDetailsViewModel;
 use crate::shared::icons::icon;
+use crate::system::presence::ui::{use_presence, PresenceStrip};
 use leptos::prelude::*;
 use std::rc::Rc;
 
@@ -10,11 +11,26 @@ pub fn OrganizationDetails(
     on_cancel: Rc<dyn Fn(())>,
 ) -> impl IntoView {
     let vm = OrganizationDetailsViewModel::new();
+    let presence_id = id.clone();
+    let presence = use_presence(
+        "a002_organization",
+        Signal::derive(move || presence_id.clone()),
+    );
     vm.load_if_needed(id);
 
     // Clone vm for multiple closures
     let vm_clone = vm.clone();
 
+    Effect::new({
+        let vm = vm_clone.clone();
+        move |_| presence.set_editing(vm.is_dirty())
+    });
+
+    let on_saved: Rc<dyn Fn(())> = Rc::new(move |_| {
+        presence.notify_saved();
+        on_saved(());
+    });
+
     view! {
         <div>
             <div class="modal-header" style="display: flex; justify-content: space-between; align-items: center;">
@@ -24,13 +40,18 @@ pub fn OrganizationDetails(
                         move || if vm.is_edit_mode()() { "Редактирование организации" } else { "Новая организация" }
                     }
                 </h3>
+                <PresenceStrip presence=presence />
                 <div style="display: flex; gap: var(--spacing-sm);">
                     <button
                         class="button button--primary"
                         on:click={
                             let vm = vm_clone.clone();
                             let on_saved = on_saved.clone();
-                            move |_| vm.save_command(on_saved.clone())
+                            move |_| {
+                                if presence.confirm_save() {
+                                    vm.save_command(on_saved.clone());
+                                }
+                            }
                         }
                         disabled={
                             let vm = vm_clone.clone();
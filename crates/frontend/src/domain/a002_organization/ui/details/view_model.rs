@@ -8,6 +8,8 @@ use std::rc::Rc;
 #[derive(Clone)]
 pub struct OrganizationDetailsViewModel {
     pub form: RwSignal<OrganizationDto>,
+    /// Состояние формы после загрузки — для отметки «есть несохранённые изменения»
+    pub loaded: RwSignal<Option<OrganizationDto>>,
     pub error: RwSignal<Option<String>>,
 }
 
@@ -15,6 +17,7 @@ impl OrganizationDetailsViewModel {
     pub fn new() -> Self {
         Self {
             form: RwSignal::new(OrganizationDto::default()),
+            loaded: RwSignal::new(None),
             error: RwSignal::new(None),
         }
     }
@@ -23,6 +26,12 @@ impl OrganizationDetailsViewModel {
         move || self.form.get().id.is_some()
     }
 
+    /// Форма загруженной записи изменена пользователем.
+    pub fn is_dirty(&self) -> bool {
+        self.loaded
+            .with(|loaded| loaded.as_ref().is_some_and(|l| self.form.with(|f| f != l)))
+    }
+
     pub fn is_form_valid(&self) -> impl Fn() -> bool + '_ {
         move || Self::validate_form(&self.form.get()).is_ok()
     }
@@ -46,6 +55,7 @@ impl OrganizationDetailsViewModel {
             return;
         };
         let form = self.form;
+        let loaded = self.loaded;
         let error = self.error;
         wasm_bindgen_futures::spawn_local(async move {
             let result = model::fetch_by_id(existing_id).await;
@@ -65,6 +75,7 @@ impl OrganizationDetailsViewModel {
                 comment: aggregate.base.comment,
                 entity_ref: aggregate.entity_ref,
             };
+            loaded.set(Some(dto.clone()));
             form.set(dto);
         });
     }
@@ -1,5 +1,6 @@
 use super::view_model::CounterpartyDetailsViewModel;
 use crate::shared::icons::icon;
+use crate::system::presence::ui::{use_presence, PresenceStrip};
 use leptos::prelude::*;
 use std::rc::Rc;
 
@@ -10,10 +11,25 @@ pub fn CounterpartyDetails(
     on_cancel: Rc<dyn Fn(())>,
 ) -> impl IntoView {
     let vm = CounterpartyDetailsViewModel::new();
+    let presence_id = id.clone();
+    let presence = use_presence(
+        "a003_counterparty",
+        Signal::derive(move || presence_id.clone()),
+    );
     vm.load_if_needed(id);
 
     let vm_clone = vm.clone();
 
+    Effect::new({
+        let vm = vm_clone.clone();
+        move |_| presence.set_editing(vm.is_dirty())
+    });
+
+    let on_saved: Rc<dyn Fn(())> = Rc::new(move |_| {
+        presence.notify_saved();
+        on_saved(());
+    });
+
     view! {
         <div class="details-container">
             <div class="details-header">
@@ -23,6 +39,7 @@ pub fn CounterpartyDetails(
                         move || if vm.is_edit_mode()() { "Редактирование контрагента" } else { "Новый контрагент" }
                     }
                 </h3>
+                <PresenceStrip presence=presence />
             </div>
 
             {
@@ -192,7 +209,9 @@ pub fn CounterpartyDetails(
                             let vm = vm_clone.clone();
                             let on_saved = on_saved.clone();
                             move |_| {
-                                vm.save_command(on_saved.clone())();
+                                if presence.confirm_save() {
+                                    vm.save_command(on_saved.clone())();
+                                }
                             }
                         }
                     >
@@ -8,6 +8,8 @@ use std::rc::Rc;
 #[derive(Clone)]
 pub struct CounterpartyDetailsViewModel {
     pub form: RwSignal<CounterpartyDto>,
+    /// Состояние формы после загрузки — для отметки «есть несохранённые изменения»
+    pub loaded: RwSignal<Option<CounterpartyDto>>,
     pub error: RwSignal<Option<String>>,
 }
 
@@ -15,6 +17,7 @@ impl CounterpartyDetailsViewModel {
     pub fn new() -> Self {
         Self {
             form: RwSignal::new(CounterpartyDto::default()),
+            loaded: RwSignal::new(None),
             error: RwSignal::new(None),
         }
     }
@@ -23,6 +26,12 @@ impl CounterpartyDetailsViewModel {
         move || self.form.get().id.is_some()
     }
 
+    /// Форма загруженной записи изменена пользователем.
+    pub fn is_dirty(&self) -> bool {
+        self.loaded
+            .with(|loaded| loaded.as_ref().is_some_and(|l| self.form.with(|f| f != l)))
+    }
+
     pub fn is_form_valid(&self) -> impl Fn() -> bool + '_ {
         move || Self::validate_form(&self.form.get()).is_ok()
     }
@@ -55,6 +64,7 @@ impl CounterpartyDetailsViewModel {
                         f.kpp = Some(item.kpp);
                         f.updated_at = Some(item.base.metadata.updated_at);
                     });
+                    this.loaded.set(Some(this.form.get_untracked()));
                 }
                 Err(e) => this.error.set(Some(e)),
             }
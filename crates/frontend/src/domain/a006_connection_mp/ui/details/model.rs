@@ -221,7 +221,7 @@ pub struct ConnectionMPFormDto {
     pub is_used: bool,
     pub business_account_id: Option<String>,
     pub api_key_stats: Option<String>,
-    pub test_mode: bool,
+    pub is_sandbox: bool,
     pub planned_commission_percent: Option<f64>,
     pub planned_acquiring_percent: Option<f64>,
     pub authorization_type: AuthorizationType,
@@ -242,7 +242,7 @@ impl Default for ConnectionMPFormDto {
             is_used: false,
             business_account_id: None,
             api_key_stats: None,
-            test_mode: false,
+            is_sandbox: false,
             planned_commission_percent: None,
             planned_acquiring_percent: None,
             authorization_type: AuthorizationType::default(),
@@ -266,7 +266,7 @@ impl From<ConnectionMP> for ConnectionMPFormDto {
             is_used: conn.is_used,
             business_account_id: conn.business_account_id,
             api_key_stats: conn.api_key_stats,
-            test_mode: conn.test_mode,
+            is_sandbox: conn.is_sandbox,
             planned_commission_percent: conn.planned_commission_percent,
             planned_acquiring_percent: conn.planned_acquiring_percent,
             authorization_type: conn.authorization_type,
@@ -289,7 +289,7 @@ impl From<ConnectionMPFormDto> for ConnectionMPDto {
             is_used: form.is_used,
             business_account_id: form.business_account_id,
             api_key_stats: form.api_key_stats,
-            test_mode: form.test_mode,
+            is_sandbox: form.is_sandbox,
             planned_commission_percent: form.planned_commission_percent,
             planned_acquiring_percent: form.planned_acquiring_percent,
             authorization_type: form.authorization_type,
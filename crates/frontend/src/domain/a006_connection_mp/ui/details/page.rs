@@ -73,6 +73,7 @@ fn Header(vm: ConnectionMPDetailsVm, id: Option<String>, on_close: Callback<()>)
         "Новое подключение"
     };
 
+    let vm_sandbox = vm.clone();
     let vm_save = vm.clone();
     let vm_test = vm.clone();
     let vm_info = vm.clone();
@@ -96,6 +97,11 @@ fn Header(vm: ConnectionMPDetailsVm, id: Option<String>, on_close: Callback<()>)
         <div class="page__header">
             <div class="page__header-left">
                 <h2>{title}</h2>
+                <Show when=move || vm_sandbox.form.get().is_sandbox>
+                    <span class="badge badge--warning" title="Запросы идут в песочницу, документы не попадают в проекции">
+                        "Песочница"
+                    </span>
+                </Show>
             </div>
             <div class="page__header-right">
                 <Button
@@ -37,7 +37,7 @@ pub fn GeneralTab(vm: ConnectionMPDetailsVm) -> impl IntoView {
     let vm_api_key_stats = vm.clone();
     let vm_authorization_type = vm.clone();
     let vm_is_used = vm.clone();
-    let vm_test_mode = vm.clone();
+    let vm_sandbox = vm.clone();
 
     let modal_stack_mp = modal_stack.clone();
     let open_marketplace_picker = move |_| {
@@ -356,6 +356,14 @@ pub fn GeneralTab(vm: ConnectionMPDetailsVm) -> impl IntoView {
                         <small class="help-text help-text--tiny">
                             {"• WB: Bearer • Ozon: Api-Key • Яндекс: API Key = Api-Key, OAuth 2.0 = Authorization: Bearer"}
                         </small>
+                        {
+                            let vm = vm_api_key.clone();
+                            move || is_masked_secret(&vm.form.get().api_key).then(|| view! {
+                                <small class="help-text help-text--tiny">
+                                    {"Ключ сохранён в зашифрованном виде и скрыт. Чтобы заменить — вставьте новый целиком."}
+                                </small>
+                            })
+                        }
                     </div>
 
                     <div class="form__group">
@@ -449,7 +457,24 @@ pub fn GeneralTab(vm: ConnectionMPDetailsVm) -> impl IntoView {
                             }
                             placeholder="WB: токен Statistics API (опц.)"
                         />
-                        <small class="help-text help-text--tiny">{"WB: отдельный токен Statistics API."}</small>
+                        <small class="help-text help-text--tiny">
+                            {
+                                let vm = vm_api_key_stats.clone();
+                                move || {
+                                    let masked = vm
+                                        .form
+                                        .get()
+                                        .api_key_stats
+                                        .as_deref()
+                                        .is_some_and(is_masked_secret);
+                                    if masked {
+                                        "WB: отдельный токен Statistics API (сохранён и скрыт; вставьте новый, чтобы заменить)."
+                                    } else {
+                                        "WB: отдельный токен Statistics API."
+                                    }
+                                }
+                            }
+                        </small>
                     </div>
 
                     <div class="form__group">
@@ -507,17 +532,17 @@ pub fn GeneralTab(vm: ConnectionMPDetailsVm) -> impl IntoView {
                         type="checkbox"
                         class="table__checkbox"
                         prop:checked={
-                            let vm = vm_test_mode.clone();
-                            move || vm.form.get().test_mode
+                            let vm = vm_sandbox.clone();
+                            move || vm.form.get().is_sandbox
                         }
                         on:change={
-                            let vm = vm_test_mode.clone();
+                            let vm = vm_sandbox.clone();
                             move |ev| {
-                                vm.form.update(|f| f.test_mode = event_target_checked(&ev));
+                                vm.form.update(|f| f.is_sandbox = event_target_checked(&ev));
                             }
                         }
                     />
-                    "Тестовый режим"
+                    "Песочница (тестовые данные)"
                 </label>
             </div>
 
@@ -581,3 +606,8 @@ pub fn GeneralTab(vm: ConnectionMPDetailsVm) -> impl IntoView {
         </div>
     }
 }
+
+/// Бэкенд отдаёт сохранённые токены маской `••••` + последние 4 символа.
+fn is_masked_secret(value: &str) -> bool {
+    value.starts_with("••••")
+}
@@ -25,7 +25,7 @@ pub struct ConnectionMPRow {
     pub marketplace: String,
     pub organization: String,
     pub is_used: bool,
-    pub test_mode: bool,
+    pub is_sandbox: bool,
     pub comment: String,
     pub created_at: String,
 }
@@ -51,7 +51,7 @@ impl ConnectionMPRow {
             marketplace,
             organization,
             is_used: c.is_used,
-            test_mode: c.test_mode,
+            is_sandbox: c.is_sandbox,
             comment: c.base.comment.unwrap_or_else(|| "-".to_string()),
             created_at: format_timestamp(c.base.metadata.created_at),
         }
@@ -74,7 +74,7 @@ impl Sortable for ConnectionMPRow {
                 .to_lowercase()
                 .cmp(&other.organization.to_lowercase()),
             "is_used" => self.is_used.cmp(&other.is_used),
-            "test_mode" => self.test_mode.cmp(&other.test_mode),
+            "is_sandbox" => self.is_sandbox.cmp(&other.is_sandbox),
             "comment" => self
                 .comment
                 .to_lowercase()
@@ -338,16 +338,16 @@ pub fn ConnectionMPList() -> impl IntoView {
                                     </span>
                                 </TableHeaderCell>
                                 <TableHeaderCell resizable=false min_width=100.0>
-                                    "Тестовый режим"
+                                    "Песочница"
                                     <span
-                                        class={move || get_sort_class(&state.sort_field.get(), "test_mode")}
+                                        class={move || get_sort_class(&state.sort_field.get(), "is_sandbox")}
                                         style="cursor: pointer; margin-left: 4px;"
                                         on:click=move |e| {
                                             e.stop_propagation();
-                                            toggle_sort("test_mode");
+                                            toggle_sort("is_sandbox");
                                         }
                                     >
-                                        {move || get_sort_indicator("test_mode", &state.sort_field.get(), state.sort_ascending.get())}
+                                        {move || get_sort_indicator("is_sandbox", &state.sort_field.get(), state.sort_ascending.get())}
                                     </span>
                                 </TableHeaderCell>
                                 <TableHeaderCell resizable=true min_width=150.0 class="resizable">
@@ -426,7 +426,7 @@ pub fn ConnectionMPList() -> impl IntoView {
                                         </TableCell>
                                         <TableCell>
                                             <TableCellLayout>
-                                                {if row.test_mode { "Да" } else { "Нет" }}
+                                                {row.is_sandbox.then(|| view! { <span class="badge badge--warning">"Песочница"</span> })}
                                             </TableCellLayout>
                                         </TableCell>
                                         <TableCell>
@@ -1,4 +1,5 @@
 mod model;
+mod price_history;
 mod view;
 mod view_model;
 
@@ -3,7 +3,7 @@ use contracts::domain::a004_nomenclature::aggregate::Nomenclature;
 use contracts::domain::a005_marketplace::aggregate::Marketplace;
 use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
 use contracts::domain::a007_marketplace_product::aggregate::{
-    MarketplaceProduct, MarketplaceProductDto,
+    MarketplaceProduct, MarketplaceProductDto, PriceHistoryResponse,
 };
 use wasm_bindgen::JsCast;
 use web_sys::{Request, RequestInit, RequestMode, Response};
@@ -219,3 +219,42 @@ pub async fn fetch_nomenclature(id: &str) -> Result<Nomenclature, String> {
     let data: Nomenclature = serde_json::from_str(&text_str).map_err(|e| format!("{e}"))?;
     Ok(data)
 }
+
+/// История цен товара по дням (заказы и продажи WB)
+pub async fn fetch_price_history(
+    id: &str,
+    date_from: &str,
+    date_to: &str,
+) -> Result<PriceHistoryResponse, String> {
+    let opts = RequestInit::new();
+    opts.set_method("GET");
+    opts.set_mode(RequestMode::Cors);
+
+    let url = format!(
+        "{}/api/a007/marketplace-product/{}/price-history?date_from={}&date_to={}",
+        api_base(),
+        id,
+        date_from,
+        date_to
+    );
+    let request = Request::new_with_str_and_init(&url, &opts).map_err(|e| format!("{e:?}"))?;
+    request
+        .headers()
+        .set("Accept", "application/json")
+        .map_err(|e| format!("{e:?}"))?;
+
+    let window = web_sys::window().ok_or_else(|| "no window".to_string())?;
+    let resp_value = wasm_bindgen_futures::JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+    let resp: Response = resp_value.dyn_into().map_err(|e| format!("{e:?}"))?;
+    if !resp.ok() {
+        return Err(format!("HTTP {}", resp.status()));
+    }
+    let text = wasm_bindgen_futures::JsFuture::from(resp.text().map_err(|e| format!("{e:?}"))?)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+    let text_str: String = text.as_string().ok_or_else(|| "bad text".to_string())?;
+    let data: PriceHistoryResponse = serde_json::from_str(&text_str).map_err(|e| format!("{e}"))?;
+    Ok(data)
+}
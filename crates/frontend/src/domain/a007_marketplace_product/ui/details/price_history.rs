@@ -0,0 +1,272 @@
+//! Вкладка «История цен» карточки товара маркетплейса: цена до скидок, цена со
+//! скидкой продавца и цена покупателя по дням, плюс СПП — видно, как
+//! софинансирование WB меняло цену для покупателя.
+
+use super::model;
+use crate::shared::money_format::{format_money_opt, format_number, format_percent_opt};
+use chrono::{Duration, Utc};
+use contracts::domain::a007_marketplace_product::aggregate::PriceHistoryPointDto;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Период по умолчанию — последние 180 дней.
+fn default_date_from() -> String {
+    (Utc::now().date_naive() - Duration::days(180))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn today() -> String {
+    Utc::now().date_naive().format("%Y-%m-%d").to_string()
+}
+
+/// Ломаная по точкам с заданным значением; разрывы ряда не соединяются.
+fn polyline_paths(
+    values: &[Option<f64>],
+    x: impl Fn(usize) -> f64,
+    y: impl Fn(f64) -> f64,
+) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut current = String::new();
+    for (index, value) in values.iter().enumerate() {
+        match value {
+            Some(value) => {
+                if !current.is_empty() {
+                    current.push(' ');
+                }
+                current.push_str(&format!("{:.1},{:.1}", x(index), y(*value)));
+            }
+            None if !current.is_empty() => paths.push(std::mem::take(&mut current)),
+            None => {}
+        }
+    }
+    if !current.is_empty() {
+        paths.push(current);
+    }
+    paths
+}
+
+fn price_chart(points: &[PriceHistoryPointDto]) -> impl IntoView {
+    let vb_w = 900.0_f64;
+    let vb_h = 260.0_f64;
+    let pad_left = 56.0;
+    let pad_right = 44.0;
+    let pad_top = 12.0;
+    let pad_bottom = 28.0;
+
+    let total: Vec<Option<f64>> = points.iter().map(|p| p.total_price).collect();
+    let with_disc: Vec<Option<f64>> = points.iter().map(|p| p.price_with_disc).collect();
+    let finished: Vec<Option<f64>> = points.iter().map(|p| p.finished_price).collect();
+    let spp: Vec<Option<f64>> = points.iter().map(|p| p.spp).collect();
+
+    let max_price = total
+        .iter()
+        .chain(with_disc.iter())
+        .chain(finished.iter())
+        .flatten()
+        .fold(0.0_f64, |max, value| max.max(*value))
+        .max(1.0);
+    let max_spp = spp
+        .iter()
+        .flatten()
+        .fold(0.0_f64, |max, value| max.max(*value))
+        .max(1.0);
+    let count = points.len().max(2);
+    let plot_w = vb_w - pad_left - pad_right;
+    let plot_h = vb_h - pad_top - pad_bottom;
+    let x = move |index: usize| pad_left + plot_w * index as f64 / (count - 1) as f64;
+    let y = move |value: f64| pad_top + plot_h * (1.0 - value / max_price);
+    let y_spp = move |value: f64| pad_top + plot_h * (1.0 - value / max_spp);
+
+    let line = |values: &[Option<f64>], scale: &dyn Fn(f64) -> f64, class: &'static str| {
+        polyline_paths(values, x, scale)
+            .into_iter()
+            .map(|points| view! { <polyline class=class points=points /> })
+            .collect_view()
+    };
+    let total_lines = line(&total, &y, "a007-price-line a007-price-line--total");
+    let disc_lines = line(&with_disc, &y, "a007-price-line a007-price-line--disc");
+    let finished_lines = line(&finished, &y, "a007-price-line a007-price-line--finished");
+    let spp_lines = line(&spp, &y_spp, "a007-price-line a007-price-line--spp");
+
+    let grid = (0..=4)
+        .map(|step| {
+            let value = max_price * step as f64 / 4.0;
+            let spp_value = max_spp * step as f64 / 4.0;
+            let gy = y(value);
+            view! {
+                <line class="a007-price-grid" x1=pad_left x2=vb_w - pad_right y1=gy y2=gy />
+                <text class="a007-price-axis" x=pad_left - 6.0 y=gy text-anchor="end" dominant-baseline="central">
+                    {format_number(value, 0)}
+                </text>
+                <text class="a007-price-axis" x=vb_w - pad_right + 6.0 y=gy dominant-baseline="central">
+                    {format!("{spp_value:.0}%")}
+                </text>
+            }
+        })
+        .collect_view();
+    // Подписи дней — не чаще, чем помещаются.
+    let label_step = (points.len() / 10).max(1);
+    let labels = points
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| index % label_step == 0)
+        .map(|(index, point)| {
+            let label = point.date.get(5..).unwrap_or(&point.date).to_string();
+            view! {
+                <text class="a007-price-axis" x=x(index) y=vb_h - 8.0 text-anchor="middle">
+                    {label}
+                </text>
+            }
+        })
+        .collect_view();
+
+    let view_box = format!("0 0 {vb_w} {vb_h}");
+    view! {
+        <svg
+            class="a007-price-chart"
+            viewBox=view_box
+            preserveAspectRatio="none"
+            role="img"
+            aria-label="История цен товара по дням"
+        >
+            {grid}
+            {labels}
+            {total_lines}
+            {disc_lines}
+            {finished_lines}
+            {spp_lines}
+        </svg>
+    }
+}
+
+#[component]
+pub fn PriceHistoryTab(product_id: String) -> impl IntoView {
+    let product_id = StoredValue::new(product_id);
+    let date_from = RwSignal::new(default_date_from());
+    let date_to = RwSignal::new(today());
+    let points = RwSignal::new(None::<Vec<PriceHistoryPointDto>>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    let load = move || {
+        let df = date_from.get_untracked();
+        let dt = date_to.get_untracked();
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match model::fetch_price_history(&product_id.get_value(), &df, &dt).await {
+                Ok(response) => points.set(Some(response.points)),
+                Err(message) => error.set(Some(message)),
+            }
+            loading.set(false);
+        });
+    };
+    load();
+
+    view! {
+        <div class="a007-price-history">
+            <style>
+                ".a007-price-history{display:flex;flex-direction:column;gap:12px}
+                .a007-price-toolbar{display:flex;gap:10px;align-items:end;flex-wrap:wrap}
+                .a007-price-toolbar label{display:flex;flex-direction:column;gap:4px;font-size:12px;color:var(--color-text-secondary)}
+                .a007-price-toolbar input{height:32px;border:1px solid var(--color-border);border-radius:6px;padding:0 8px;background:var(--color-surface);color:var(--color-text-primary)}
+                .a007-price-btn{height:32px;border:1px solid var(--color-border);border-radius:6px;background:var(--color-surface);color:var(--color-text-primary);padding:0 12px;cursor:pointer}
+                .a007-price-chart{width:100%;height:260px;display:block}
+                .a007-price-grid{stroke:var(--color-border-light,var(--color-border));stroke-width:1}
+                .a007-price-axis{font-size:10px;fill:var(--color-text-secondary)}
+                .a007-price-line{fill:none;stroke-width:2;vector-effect:non-scaling-stroke}
+                .a007-price-line--total{stroke:#94a3b8}
+                .a007-price-line--disc{stroke:#2563eb}
+                .a007-price-line--finished{stroke:#16a34a}
+                .a007-price-line--spp{stroke:#f59e0b;stroke-dasharray:6 4}
+                .a007-price-legend{display:flex;gap:16px;flex-wrap:wrap;font-size:12px;color:var(--color-text-secondary)}
+                .a007-price-legend span::before{content:'';display:inline-block;width:14px;height:3px;margin-right:6px;vertical-align:middle;background:currentColor}
+                .a007-price-table{width:100%;border-collapse:collapse;font-size:13px}
+                .a007-price-table th{text-align:left;border-bottom:1px solid var(--color-border);padding:6px 8px;color:var(--color-text-secondary);font-weight:600}
+                .a007-price-table td{border-bottom:1px solid var(--color-border-light,var(--color-border));padding:5px 8px}
+                .a007-price-num{text-align:right;font-variant-numeric:tabular-nums}
+                .a007-price-state{padding:18px;color:var(--color-text-secondary)}"
+            </style>
+
+            <div class="a007-price-toolbar">
+                <label>
+                    "Дни с"
+                    <input
+                        type="date"
+                        prop:value=move || date_from.get()
+                        on:input=move |ev| date_from.set(event_target_value(&ev))
+                    />
+                </label>
+                <label>
+                    "по"
+                    <input
+                        type="date"
+                        prop:value=move || date_to.get()
+                        on:input=move |ev| date_to.set(event_target_value(&ev))
+                    />
+                </label>
+                <button class="a007-price-btn" on:click=move |_| load() disabled=move || loading.get()>
+                    {move || if loading.get() { "Загрузка..." } else { "Обновить" }}
+                </button>
+            </div>
+
+            {move || error.get().map(|message| view! { <div class="a007-price-state">{message}</div> })}
+
+            {move || {
+                let Some(points) = points.get() else {
+                    return view! { <div class="a007-price-state">"Загрузка..."</div> }.into_any();
+                };
+                if points.is_empty() {
+                    return view! {
+                        <div class="a007-price-state">"За период нет заказов и продаж WB по товару."</div>
+                    }
+                    .into_any();
+                }
+                view! {
+                    <div class="a007-price-legend">
+                        <span style="color:#94a3b8">"Цена до скидок"</span>
+                        <span style="color:#2563eb">"Цена со скидкой продавца"</span>
+                        <span style="color:#16a34a">"Цена покупателя"</span>
+                        <span style="color:#f59e0b">"СПП, % (правая ось)"</span>
+                    </div>
+                    {price_chart(&points)}
+                    <table class="a007-price-table">
+                        <thead>
+                            <tr>
+                                <th>"День"</th>
+                                <th class="a007-price-num">"До скидок"</th>
+                                <th class="a007-price-num">"Со скидкой"</th>
+                                <th class="a007-price-num">"СПП"</th>
+                                <th class="a007-price-num">"Покупателю"</th>
+                                <th class="a007-price-num">"Софинансирование WB"</th>
+                                <th class="a007-price-num">"Документов"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {points
+                                .into_iter()
+                                .rev()
+                                .map(|point| {
+                                    let coinvestment = point.wb_coinvestment();
+                                    view! {
+                                        <tr>
+                                            <td>{point.date.clone()}</td>
+                                            <td class="a007-price-num">{format_money_opt(point.total_price)}</td>
+                                            <td class="a007-price-num">{format_money_opt(point.price_with_disc)}</td>
+                                            <td class="a007-price-num">{format_percent_opt(point.spp, 1)}</td>
+                                            <td class="a007-price-num">{format_money_opt(point.finished_price)}</td>
+                                            <td class="a007-price-num">{format_money_opt(coinvestment)}</td>
+                                            <td class="a007-price-num">{point.documents}</td>
+                                        </tr>
+                                    }
+                                })
+                                .collect_view()}
+                        </tbody>
+                    </table>
+                }
+                .into_any()
+            }}
+        </div>
+    }
+}
@@ -1,3 +1,4 @@
+use super::price_history::PriceHistoryTab;
 use super::view_model::MarketplaceProductDetailsViewModel;
 use crate::domain::a004_nomenclature::ui::picker::NomenclaturePicker;
 use crate::shared::components::ui::FieldDisplayReactive;
@@ -21,7 +22,9 @@ pub fn MarketplaceProductDetails(
     let vm = MarketplaceProductDetailsViewModel::new();
     // Сохраняем id для избранного до того, как он будет передан во view-model.
     let favorite_id = id.clone();
+    let price_history_id = id.clone();
     vm.load_if_needed(id);
+    let active_tab = RwSignal::new("general");
 
     let vm_favorite = vm.clone();
     let favorite_title = Signal::derive(move || {
@@ -239,6 +242,25 @@ pub fn MarketplaceProductDetails(
                 </div>
             </div>
 
+            {price_history_id.is_some().then(|| view! {
+                <div class="page__tabs">
+                    <button
+                        class="page__tab"
+                        class:page__tab--active=move || active_tab.get() == "general"
+                        on:click=move |_| active_tab.set("general")
+                    >
+                        {icon("file-text")} "Основное"
+                    </button>
+                    <button
+                        class="page__tab"
+                        class:page__tab--active=move || active_tab.get() == "prices"
+                        on:click=move |_| active_tab.set("prices")
+                    >
+                        {icon("bar-chart")} "История цен"
+                    </button>
+                </div>
+            })}
+
             <div class="page__content">
                 {
                     let vm = vm_error.clone();
@@ -259,280 +281,289 @@ pub fn MarketplaceProductDetails(
                     })
                 }
 
-                <div style="display: grid; grid-template-columns: repeat(auto-fit, minmax(400px, 1fr)); gap: var(--spacing-xl);">
-                    <Card>
-                        <Flex vertical=true gap=FlexGap::Medium>
-                            <h3 style="margin: 0; font-size: var(--font-size-base); font-weight: 600;">"Основная информация"</h3>
-
-                            <Flex vertical=true gap=FlexGap::Small>
-                                <Label>"Описание"</Label>
-                                <Input value=description placeholder="Краткое описание товара" />
-                            </Flex>
+                // Форма скрывается, а не пересоздаётся, чтобы не терять несохранённые правки.
+                <div style:display=move || if active_tab.get() == "general" { "block" } else { "none" }>
+                    <div style="display: grid; grid-template-columns: repeat(auto-fit, minmax(400px, 1fr)); gap: var(--spacing-xl);">
+                        <Card>
+                            <Flex vertical=true gap=FlexGap::Medium>
+                                <h3 style="margin: 0; font-size: var(--font-size-base); font-weight: 600;">"Основная информация"</h3>
 
-                            <Flex gap=FlexGap::Medium>
-                                <Flex vertical=true gap=FlexGap::Small style="flex: 1;">
-                                    <Label>"Маркетплейс"</Label>
-                                    <Input value=vm.marketplace_name.clone() disabled=Signal::derive(|| true) />
-                                </Flex>
-                                <Flex vertical=true gap=FlexGap::Small style="flex: 1;">
-                                    <Label>"Кабинет"</Label>
-                                    <Input value=vm.connection_name.clone() disabled=Signal::derive(|| true) />
+                                <Flex vertical=true gap=FlexGap::Small>
+                                    <Label>"Описание"</Label>
+                                    <Input value=description placeholder="Краткое описание товара" />
                                 </Flex>
-                            </Flex>
 
-                            <Flex gap=FlexGap::Medium>
-                                <Flex vertical=true gap=FlexGap::Small style="flex: 1;">
-                                    <Label>"SKU маркетплейса"</Label>
-                                    <Input value=marketplace_sku />
+                                <Flex gap=FlexGap::Medium>
+                                    <Flex vertical=true gap=FlexGap::Small style="flex: 1;">
+                                        <Label>"Маркетплейс"</Label>
+                                        <Input value=vm.marketplace_name.clone() disabled=Signal::derive(|| true) />
+                                    </Flex>
+                                    <Flex vertical=true gap=FlexGap::Small style="flex: 1;">
+                                        <Label>"Кабинет"</Label>
+                                        <Input value=vm.connection_name.clone() disabled=Signal::derive(|| true) />
+                                    </Flex>
                                 </Flex>
-                                <Flex vertical=true gap=FlexGap::Small style="flex: 1;">
-                                    <Label>"Артикул"</Label>
-                                    <Input value=article />
+
+                                <Flex gap=FlexGap::Medium>
+                                    <Flex vertical=true gap=FlexGap::Small style="flex: 1;">
+                                        <Label>"SKU маркетплейса"</Label>
+                                        <Input value=marketplace_sku />
+                                    </Flex>
+                                    <Flex vertical=true gap=FlexGap::Small style="flex: 1;">
+                                        <Label>"Артикул"</Label>
+                                        <Input value=article />
+                                    </Flex>
                                 </Flex>
-                            </Flex>
 
-                            {move || {
-                                // Блок «Быстрый доступ» отображается только для товаров Wildberries.
-                                if !vm_product_link.is_wildberries() {
-                                    return view! { <></> }.into_any();
-                                }
-                                view! {
-                                    <div class="a007-link-card__summary a007-quick-access">
-                                        <div class="a007-link-card__summary-label">"Быстрый доступ"</div>
-                                        <div class="a007-link-card__summary-value">
-                                            {match vm_product_link.marketplace_product_url() {
-                                                Some(url) => view! {
-                                                    <a
-                                                        href=url
-                                                        target="_blank"
-                                                        rel="noopener noreferrer"
-                                                        class="a007-quick-access__link"
-                                                    >
-                                                        {icon("link")}
-                                                        "Открыть карточку на Wildberries"
-                                                    </a>
-                                                }
-                                                .into_any(),
-                                                None => view! {
-                                                    <span>"Заполните SKU, чтобы собрать ссылку на карточку товара Wildberries."</span>
-                                                }
-                                                .into_any(),
-                                            }}
+                                {move || {
+                                    // Блок «Быстрый доступ» отображается только для товаров Wildberries.
+                                    if !vm_product_link.is_wildberries() {
+                                        return view! { <></> }.into_any();
+                                    }
+                                    view! {
+                                        <div class="a007-link-card__summary a007-quick-access">
+                                            <div class="a007-link-card__summary-label">"Быстрый доступ"</div>
+                                            <div class="a007-link-card__summary-value">
+                                                {match vm_product_link.marketplace_product_url() {
+                                                    Some(url) => view! {
+                                                        <a
+                                                            href=url
+                                                            target="_blank"
+                                                            rel="noopener noreferrer"
+                                                            class="a007-quick-access__link"
+                                                        >
+                                                            {icon("link")}
+                                                            "Открыть карточку на Wildberries"
+                                                        </a>
+                                                    }
+                                                    .into_any(),
+                                                    None => view! {
+                                                        <span>"Заполните SKU, чтобы собрать ссылку на карточку товара Wildberries."</span>
+                                                    }
+                                                    .into_any(),
+                                                }}
+                                            </div>
                                         </div>
+                                    }
+                                    .into_any()
+                                }}
+
+                                <div class="a007-link-card__summary">
+                                    <div class="a007-link-card__summary-label">"Оперативная сводка"</div>
+                                    <div
+                                        class="a007-link-card__summary-value"
+                                        style="display: grid; grid-template-columns: repeat(auto-fit, minmax(180px, 1fr)); gap: 12px;"
+                                    >
+                                        {move || {
+                                            let form = vm_operational_info.form.get();
+                                            let last_update = form
+                                                .last_update
+                                                .map(|d| format_datetime(&d.to_rfc3339()))
+                                                .unwrap_or_else(|| "—".to_string());
+                                            let category = form
+                                                .category_name
+                                                .clone()
+                                                .filter(|v| !v.trim().is_empty())
+                                                .unwrap_or_else(|| "—".to_string());
+                                            let brand = form
+                                                .brand
+                                                .clone()
+                                                .filter(|v| !v.trim().is_empty())
+                                                .unwrap_or_else(|| "—".to_string());
+                                            let barcode = form
+                                                .barcode
+                                                .clone()
+                                                .filter(|v| !v.trim().is_empty())
+                                                .unwrap_or_else(|| "—".to_string());
+
+                                            view! {
+                                                <>
+                                                    <div>
+                                                        <strong>"Обновлено:"</strong>
+                                                        <div>{last_update}</div>
+                                                    </div>
+                                                    <div>
+                                                        <strong>"Категория:"</strong>
+                                                        <div>{category}</div>
+                                                    </div>
+                                                    <div>
+                                                        <strong>"Бренд:"</strong>
+                                                        <div>{brand}</div>
+                                                    </div>
+                                                    <div>
+                                                        <strong>"Штрихкод:"</strong>
+                                                        <div>{barcode}</div>
+                                                    </div>
+                                                </>
+                                            }
+                                        }}
                                     </div>
-                                }
-                                .into_any()
-                            }}
-
-                            <div class="a007-link-card__summary">
-                                <div class="a007-link-card__summary-label">"Оперативная сводка"</div>
-                                <div
-                                    class="a007-link-card__summary-value"
-                                    style="display: grid; grid-template-columns: repeat(auto-fit, minmax(180px, 1fr)); gap: 12px;"
-                                >
-                                    {move || {
-                                        let form = vm_operational_info.form.get();
-                                        let last_update = form
-                                            .last_update
-                                            .map(|d| format_datetime(&d.to_rfc3339()))
-                                            .unwrap_or_else(|| "—".to_string());
-                                        let category = form
-                                            .category_name
-                                            .clone()
-                                            .filter(|v| !v.trim().is_empty())
-                                            .unwrap_or_else(|| "—".to_string());
-                                        let brand = form
-                                            .brand
-                                            .clone()
-                                            .filter(|v| !v.trim().is_empty())
-                                            .unwrap_or_else(|| "—".to_string());
-                                        let barcode = form
-                                            .barcode
-                                            .clone()
-                                            .filter(|v| !v.trim().is_empty())
-                                            .unwrap_or_else(|| "—".to_string());
-
-                                        view! {
-                                            <>
-                                                <div>
-                                                    <strong>"Обновлено:"</strong>
-                                                    <div>{last_update}</div>
-                                                </div>
-                                                <div>
-                                                    <strong>"Категория:"</strong>
-                                                    <div>{category}</div>
-                                                </div>
-                                                <div>
-                                                    <strong>"Бренд:"</strong>
-                                                    <div>{brand}</div>
-                                                </div>
-                                                <div>
-                                                    <strong>"Штрихкод:"</strong>
-                                                    <div>{barcode}</div>
-                                                </div>
-                                            </>
-                                        }
-                                    }}
                                 </div>
-                            </div>
 
-                            <Flex gap=FlexGap::Medium>
-                                <Flex vertical=true gap=FlexGap::Small style="flex: 1;">
-                                    <Label>"Штрихкод"</Label>
-                                    <Input value=barcode />
-                                </Flex>
-                                <Flex vertical=true gap=FlexGap::Small style="flex: 1;">
-                                    <Label>"Бренд"</Label>
-                                    <Input value=brand />
+                                <Flex gap=FlexGap::Medium>
+                                    <Flex vertical=true gap=FlexGap::Small style="flex: 1;">
+                                        <Label>"Штрихкод"</Label>
+                                        <Input value=barcode />
+                                    </Flex>
+                                    <Flex vertical=true gap=FlexGap::Small style="flex: 1;">
+                                        <Label>"Бренд"</Label>
+                                        <Input value=brand />
+                                    </Flex>
                                 </Flex>
                             </Flex>
-                        </Flex>
-                    </Card>
-
-                    <Card>
-                        <div class="a007-link-card">
-                            <div class="a007-link-card__header">
-                                <h3 class="a007-link-card__title">"Связь с 1С УТ"</h3>
-                                <span class=move || {
-                                    if vm.form.get().nomenclature_ref.is_some() {
-                                        "badge badge--success"
-                                    } else {
-                                        "badge badge--error"
-                                    }
-                                }>
-                                    {move || {
+                        </Card>
+
+                        <Card>
+                            <div class="a007-link-card">
+                                <div class="a007-link-card__header">
+                                    <h3 class="a007-link-card__title">"Связь с 1С УТ"</h3>
+                                    <span class=move || {
                                         if vm.form.get().nomenclature_ref.is_some() {
-                                            "Связана"
+                                            "badge badge--success"
                                         } else {
-                                            "Не связана"
+                                            "badge badge--error"
                                         }
-                                    }}
-                                </span>
-                            </div>
+                                    }>
+                                        {move || {
+                                            if vm.form.get().nomenclature_ref.is_some() {
+                                                "Связана"
+                                            } else {
+                                                "Не связана"
+                                            }
+                                        }}
+                                    </span>
+                                </div>
 
-                            <p class="a007-link-card__hint">
-                                "Автоподбор по артикулу ищет точное совпадение в номенклатуре 1С. Автоподбор по штрихкоду использует таблицу штрихкодов p901. Если найдено несколько вариантов, откроется выбор."
-                            </p>
-
-                            <div class="a007-link-card__summary">
-                                <div class="a007-link-card__summary-label">"Основание для автоподбора"</div>
-                                <div class="a007-link-card__summary-value">
-                                    {move || {
-                                        let form = vm.form.get();
-                                        let article = form.article.trim();
-                                        let barcode = form.barcode.as_deref().map(str::trim).unwrap_or("");
-                                        if article.is_empty() && barcode.is_empty() {
-                                            "Артикул и штрихкод товара маркетплейса не заполнены".to_string()
-                                        } else {
-                                            format!(
-                                                "Артикул: {} | Штрихкод: {}",
-                                                if article.is_empty() { "—" } else { article },
-                                                if barcode.is_empty() { "—" } else { barcode },
-                                            )
-                                        }
-                                    }}
+                                <p class="a007-link-card__hint">
+                                    "Автоподбор по артикулу ищет точное совпадение в номенклатуре 1С. Автоподбор по штрихкоду использует таблицу штрихкодов p901. Если найдено несколько вариантов, откроется выбор."
+                                </p>
+
+                                <div class="a007-link-card__summary">
+                                    <div class="a007-link-card__summary-label">"Основание для автоподбора"</div>
+                                    <div class="a007-link-card__summary-value">
+                                        {move || {
+                                            let form = vm.form.get();
+                                            let article = form.article.trim();
+                                            let barcode = form.barcode.as_deref().map(str::trim).unwrap_or("");
+                                            if article.is_empty() && barcode.is_empty() {
+                                                "Артикул и штрихкод товара маркетплейса не заполнены".to_string()
+                                            } else {
+                                                format!(
+                                                    "Артикул: {} | Штрихкод: {}",
+                                                    if article.is_empty() { "—" } else { article },
+                                                    if barcode.is_empty() { "—" } else { barcode },
+                                                )
+                                            }
+                                        }}
+                                    </div>
                                 </div>
-                            </div>
 
-                            <div class="form__group">
-                                <label class="form__label">"Связанная номенклатура 1С"</label>
-                                <FieldDisplayReactive value=Signal::derive({
-                                    let name = vm.nomenclature_name;
-                                    move || {
-                                        let value = name.get();
-                                        if value.trim().is_empty() {
-                                            "Связь еще не выбрана".to_string()
-                                        } else {
-                                            value
+                                <div class="form__group">
+                                    <label class="form__label">"Связанная номенклатура 1С"</label>
+                                    <FieldDisplayReactive value=Signal::derive({
+                                        let name = vm.nomenclature_name;
+                                        move || {
+                                            let value = name.get();
+                                            if value.trim().is_empty() {
+                                                "Связь еще не выбрана".to_string()
+                                            } else {
+                                                value
+                                            }
                                         }
-                                    }
-                                }) />
-                            </div>
-
-                            <div class="a007-link-card__actions">
-                                <Button
-                                    appearance=ButtonAppearance::Primary
-                                    on_click={
-                                        let vm = vm_search_nom.clone();
-                                        move |_| vm.search_nomenclature_by_article()
-                                    }
-                                    disabled=Signal::derive({
-                                        let vm = vm_search_nom_disabled.clone();
-                                        move || vm.form.get().article.trim().is_empty()
-                                    })
-                                >
-                                    {icon("search")}
-                                    " Автоподбор по артикулу"
-                                </Button>
-                                <Button
-                                    appearance=ButtonAppearance::Primary
-                                    on_click={
-                                        let vm = vm_search_barcode.clone();
-                                        move |_| vm.search_nomenclature_by_barcode()
-                                    }
-                                    disabled=Signal::derive({
-                                        let vm = vm_search_barcode_disabled.clone();
-                                        move || vm.form.get().barcode.map(|b| b.trim().is_empty()).unwrap_or(true)
-                                    })
-                                >
-                                    {icon("search")}
-                                    " Автоподбор по штрихкоду"
-                                </Button>
-                                <Button
-                                    appearance=ButtonAppearance::Secondary
-                                    on_click={
-                                        let vm = vm_open_picker.clone();
-                                        move |_| vm.open_picker()
-                                    }
-                                >
-                                    {icon("list")}
-                                    " Выбрать вручную"
-                                </Button>
-                                <Button
-                                    appearance=ButtonAppearance::Subtle
-                                    on_click={
-                                        let vm = vm_clear_nom.clone();
-                                        move |_| vm.clear_nomenclature()
-                                    }
-                                    disabled=Signal::derive({
-                                        let vm = vm_clear_nom_disabled.clone();
-                                        move || vm.form.get().nomenclature_ref.is_none()
-                                    })
-                                >
-                                    {icon("x")}
-                                    " Очистить связь"
-                                </Button>
-                            </div>
+                                    }) />
+                                </div>
 
-                            <div class="a007-link-card__actions-note">
-                                "Ручной выбор открывает список номенклатуры 1С. Очистка снимает текущую связь и не меняет карточку товара маркетплейса."
-                            </div>
+                                <div class="a007-link-card__actions">
+                                    <Button
+                                        appearance=ButtonAppearance::Primary
+                                        on_click={
+                                            let vm = vm_search_nom.clone();
+                                            move |_| vm.search_nomenclature_by_article()
+                                        }
+                                        disabled=Signal::derive({
+                                            let vm = vm_search_nom_disabled.clone();
+                                            move || vm.form.get().article.trim().is_empty()
+                                        })
+                                    >
+                                        {icon("search")}
+                                        " Автоподбор по артикулу"
+                                    </Button>
+                                    <Button
+                                        appearance=ButtonAppearance::Primary
+                                        on_click={
+                                            let vm = vm_search_barcode.clone();
+                                            move |_| vm.search_nomenclature_by_barcode()
+                                        }
+                                        disabled=Signal::derive({
+                                            let vm = vm_search_barcode_disabled.clone();
+                                            move || vm.form.get().barcode.map(|b| b.trim().is_empty()).unwrap_or(true)
+                                        })
+                                    >
+                                        {icon("search")}
+                                        " Автоподбор по штрихкоду"
+                                    </Button>
+                                    <Button
+                                        appearance=ButtonAppearance::Secondary
+                                        on_click={
+                                            let vm = vm_open_picker.clone();
+                                            move |_| vm.open_picker()
+                                        }
+                                    >
+                                        {icon("list")}
+                                        " Выбрать вручную"
+                                    </Button>
+                                    <Button
+                                        appearance=ButtonAppearance::Subtle
+                                        on_click={
+                                            let vm = vm_clear_nom.clone();
+                                            move |_| vm.clear_nomenclature()
+                                        }
+                                        disabled=Signal::derive({
+                                            let vm = vm_clear_nom_disabled.clone();
+                                            move || vm.form.get().nomenclature_ref.is_none()
+                                        })
+                                    >
+                                        {icon("x")}
+                                        " Очистить связь"
+                                    </Button>
+                                </div>
 
-                            <div style="display: grid; grid-template-columns: 1fr 1fr; gap: var(--spacing-sm);">
-                                <div class="form__group">
-                                    <label class="form__label">"Код 1С"</label>
-                                    <FieldDisplayReactive value=vm.nomenclature_code />
+                                <div class="a007-link-card__actions-note">
+                                    "Ручной выбор открывает список номенклатуры 1С. Очистка снимает текущую связь и не меняет карточку товара маркетплейса."
                                 </div>
-                                <div class="form__group">
-                                    <label class="form__label">"Артикул 1С"</label>
-                                    <FieldDisplayReactive value=vm.nomenclature_article />
+
+                                <div style="display: grid; grid-template-columns: 1fr 1fr; gap: var(--spacing-sm);">
+                                    <div class="form__group">
+                                        <label class="form__label">"Код 1С"</label>
+                                        <FieldDisplayReactive value=vm.nomenclature_code />
+                                    </div>
+                                    <div class="form__group">
+                                        <label class="form__label">"Артикул 1С"</label>
+                                        <FieldDisplayReactive value=vm.nomenclature_article />
+                                    </div>
                                 </div>
                             </div>
-                        </div>
-                    </Card>
-                </div>
+                        </Card>
+                    </div>
 
-                <div style="margin-top: var(--spacing-xl);">
-                    <Card>
-                        <Flex vertical=true gap=FlexGap::Small>
-                            <Label>"Комментарий"</Label>
-                            <Input
-                                value=comment
-                                placeholder="Дополнительная информация (необязательно)"
-                            />
-                        </Flex>
-                    </Card>
+                    <div style="margin-top: var(--spacing-xl);">
+                        <Card>
+                            <Flex vertical=true gap=FlexGap::Small>
+                                <Label>"Комментарий"</Label>
+                                <Input
+                                    value=comment
+                                    placeholder="Дополнительная информация (необязательно)"
+                                />
+                            </Flex>
+                        </Card>
+                    </div>
                 </div>
+
+                {price_history_id.map(|product_id| view! {
+                    <Show when=move || active_tab.get() == "prices">
+                        <PriceHistoryTab product_id=product_id.clone() />
+                    </Show>
+                })}
             </div>
 
             {
@@ -3,6 +3,9 @@ mod state;
 use self::state::create_state;
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::api_utils::api_base;
+use crate::shared::components::abc_xyz_badge::{
+    abc_filter_options, abc_xyz_badge, xyz_filter_options,
+};
 use crate::shared::components::pagination_controls::PaginationControls;
 use crate::shared::components::ui::badge::Badge;
 use crate::shared::export::{export_to_excel, ExcelExportable};
@@ -11,7 +14,9 @@ use crate::shared::list_utils::{get_sort_class, get_sort_indicator, highlight_ma
 use crate::shared::page_frame::PageFrame;
 use contracts::domain::a005_marketplace::aggregate::Marketplace;
 use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
-use contracts::domain::a007_marketplace_product::aggregate::MarketplaceProductListItemDto;
+use contracts::domain::a007_marketplace_product::aggregate::{
+    MarketplaceProductListItemDto, StockAlertBadgeDto, StockAlertKind,
+};
 use contracts::domain::common::AggregateId;
 use gloo_net::http::Request;
 use leptos::logging::log;
@@ -51,6 +56,38 @@ pub struct PaginatedResponse {
     pub total_pages: usize,
 }
 
+/// Бейдж складского алерта (task026): мало запаса / залежалый товар.
+fn stock_alert_badge(alert: Option<StockAlertBadgeDto>) -> AnyView {
+    match alert {
+        Some(alert) => match alert.kind {
+            StockAlertKind::LowCover => {
+                let title = format!("Остаток {} шт.", alert.stock);
+                let days = alert.days_of_cover.unwrap_or_default();
+                view! {
+                    <span title=title>
+                        <Badge variant="warning".to_string()>{format!("Запас {:.0} дн.", days)}</Badge>
+                    </span>
+                }
+                .into_any()
+            }
+            StockAlertKind::StaleStock => {
+                let title = format!(
+                    "Остаток {} шт., последняя продажа: {}",
+                    alert.stock,
+                    alert.last_sale_date.unwrap_or_else(|| "нет".to_string())
+                );
+                view! {
+                    <span title=title>
+                        <Badge variant="error".to_string()>"Нет продаж"</Badge>
+                    </span>
+                }
+                .into_any()
+            }
+        },
+        None => view! { <></> }.into_any(),
+    }
+}
+
 #[component]
 #[allow(non_snake_case)]
 pub fn MarketplaceProductList() -> impl IntoView {
@@ -81,6 +118,8 @@ pub fn MarketplaceProductList() -> impl IntoView {
             .unwrap_or_default(),
     );
     let filter_problems_only = RwSignal::new(state.get_untracked().problems_only);
+    let filter_abc = RwSignal::new(state.get_untracked().abc_class.clone().unwrap_or_default());
+    let filter_xyz = RwSignal::new(state.get_untracked().xyz_class.clone().unwrap_or_default());
 
     Effect::new(move || {
         let text = search_text.get();
@@ -126,6 +165,18 @@ pub fn MarketplaceProductList() -> impl IntoView {
         });
     });
 
+    Effect::new(move || {
+        let abc = filter_abc.get();
+        let xyz = filter_xyz.get();
+        untrack(move || {
+            state.update(|s| {
+                s.abc_class = if abc.is_empty() { None } else { Some(abc) };
+                s.xyz_class = if xyz.is_empty() { None } else { Some(xyz) };
+                s.page = 0;
+            });
+        });
+    });
+
     let load_data = move || {
         let current_state = state.get_untracked();
         set_loading.set(true);
@@ -154,6 +205,12 @@ pub fn MarketplaceProductList() -> impl IntoView {
             if current_state.problems_only {
                 url.push_str("&problems_only=true");
             }
+            if let Some(ref abc) = current_state.abc_class {
+                url.push_str(&format!("&abc_class={}", urlencoding::encode(abc)));
+            }
+            if let Some(ref xyz) = current_state.xyz_class {
+                url.push_str(&format!("&xyz_class={}", urlencoding::encode(xyz)));
+            }
             if !current_state.search.is_empty() {
                 url.push_str(&format!(
                     "&search={}",
@@ -274,6 +331,17 @@ pub fn MarketplaceProductList() -> impl IntoView {
         }
     });
 
+    let classes_first_run = StoredValue::new(true);
+    Effect::new(move || {
+        let _ = filter_abc.get();
+        let _ = filter_xyz.get();
+        if !classes_first_run.get_value() {
+            load_data();
+        } else {
+            classes_first_run.set_value(false);
+        }
+    });
+
     let toggle_sort = move |field: &'static str| {
         move |_| {
             state.update(|s| {
@@ -396,6 +464,9 @@ pub fn MarketplaceProductList() -> impl IntoView {
         if s.problems_only {
             count += 1;
         }
+        if s.abc_class.is_some() || s.xyz_class.is_some() {
+            count += 1;
+        }
         count
     });
 
@@ -405,12 +476,16 @@ pub fn MarketplaceProductList() -> impl IntoView {
             s.marketplace_ref = None;
             s.connection_mp_ref = None;
             s.problems_only = false;
+            s.abc_class = None;
+            s.xyz_class = None;
             s.page = 0;
         });
         search_text.set(String::new());
         filter_marketplace.set(String::new());
         filter_connection.set(String::new());
         filter_problems_only.set(false);
+        filter_abc.set(String::new());
+        filter_xyz.set(String::new());
         load_data();
     };
 
@@ -584,6 +659,26 @@ pub fn MarketplaceProductList() -> impl IntoView {
                                     </Field>
                                 </Flex>
                             </div>
+                            <div style="width: 90px;">
+                                <Flex vertical=true gap=FlexGap::Small>
+                                    <Label>"ABC:"</Label>
+                                    <Select value=filter_abc>
+                                        {abc_filter_options().into_iter().map(|(value, label)| {
+                                            view! { <option value=value>{label}</option> }
+                                        }).collect_view()}
+                                    </Select>
+                                </Flex>
+                            </div>
+                            <div style="width: 90px;">
+                                <Flex vertical=true gap=FlexGap::Small>
+                                    <Label>"XYZ:"</Label>
+                                    <Select value=filter_xyz>
+                                        {xyz_filter_options().into_iter().map(|(value, label)| {
+                                            view! { <option value=value>{label}</option> }
+                                        }).collect_view()}
+                                    </Select>
+                                </Flex>
+                            </div>
                             <thaw::Button
                                 appearance=ButtonAppearance::Subtle
                                 on_click=move |_| clear_all_filters(())
@@ -651,6 +746,8 @@ pub fn MarketplaceProductList() -> impl IntoView {
                                     <span class={move || get_sort_class("barcode", &state.get().sort_field)}>{move || get_sort_indicator("barcode", &state.get().sort_field, state.get().sort_ascending)}</span>
                                 </th>
                                 <th class="table__header-cell table__header-cell--center a007-mp-list__sticky-cell">"1С"</th>
+                                <th class="table__header-cell a007-mp-list__sticky-cell">"Склад"</th>
+                                <th class="table__header-cell a007-mp-list__sticky-cell" title="ABC/XYZ-класс последнего квартала">"ABC/XYZ"</th>
                             </tr>
                         </thead>
                         <tbody>
@@ -700,6 +797,8 @@ pub fn MarketplaceProductList() -> impl IntoView {
                                                     view! { <span style="color: var(--color-text-tertiary);">"—"</span> }.into_any()
                                                 }}
                                             </td>
+                                            <td class="table__cell">{stock_alert_badge(item.stock_alert)}</td>
+                                            <td class="table__cell">{abc_xyz_badge(item.abc_xyz)}</td>
                                         </tr>
                                     }
                                 }).collect_view()
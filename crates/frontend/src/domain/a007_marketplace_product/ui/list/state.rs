@@ -6,6 +6,9 @@ pub struct MarketplaceProductListState {
     pub marketplace_ref: Option<String>,
     pub connection_mp_ref: Option<String>,
     pub problems_only: bool,
+    /// Класс ABC/XYZ последнего рассчитанного квартала (task027).
+    pub abc_class: Option<String>,
+    pub xyz_class: Option<String>,
     pub search: String,
 
     // Сортировка
@@ -31,6 +34,8 @@ impl Default for MarketplaceProductListState {
             marketplace_ref: None,
             connection_mp_ref: None,
             problems_only: false,
+            abc_class: None,
+            xyz_class: None,
             search: String::new(),
             sort_field: "code".to_string(),
             sort_ascending: true,
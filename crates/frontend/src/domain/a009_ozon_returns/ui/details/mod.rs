@@ -1,5 +1,6 @@
 use crate::shared::api_utils::api_base;
 use crate::shared::date_utils::format_datetime;
+use crate::system::return_dispositions::ui::ReturnDispositionPanel;
 use gloo_net::http::Request;
 use leptos::logging::log;
 use leptos::prelude::*;
@@ -92,6 +93,7 @@ pub fn OzonReturnsDetail(
     // Клонируем id для использования в разных замыканиях
     let id_for_effect = id.clone();
     let id_for_view = id.clone();
+    let id_for_panel = id.clone();
 
     // Загрузить детальные данные
     Effect::new(move || {
@@ -288,6 +290,9 @@ pub fn OzonReturnsDetail(
                         view! { <div></div> }.into_any()
                     }
                 }}
+                <div style="padding: 0 20px 20px;">
+                    <ReturnDispositionPanel entity_type="a009_ozon_returns" document_id=id_for_panel />
+                </div>
             </div>
         </div>
     }
@@ -1,5 +1,7 @@
 use crate::domain::a014_ozon_transactions::ui::details::OzonTransactionsDetail;
 use crate::shared::api_utils::api_base;
+use crate::shared::components::sandbox_badge::SandboxBadge;
+use contracts::shared::marketplace_links::MarketplaceLinkDto;
 use gloo_net::http::Request;
 use leptos::logging::log;
 use leptos::prelude::*;
@@ -16,6 +18,10 @@ pub struct OzonFbsPostingDetailDto {
     pub state: StateDto,
     pub source_meta: SourceMetaDto,
     pub metadata: MetadataDto,
+    #[serde(default)]
+    pub is_sandbox: bool,
+    #[serde(default)]
+    pub marketplace_links: Vec<MarketplaceLinkDto>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -323,13 +329,36 @@ pub fn OzonFbsPostingDetail(
     view! {
         <div class="posting-detail" style="padding: var(--spacing-xl); height: 100%; display: flex; flex-direction: column; background: var(--color-bg-primary); border-radius: var(--radius-lg); box-shadow: var(--shadow-sm);">
             <div style="background: linear-gradient(135deg, #4a5568 0%, #2d3748 100%); padding: var(--spacing-md) var(--spacing-xl); border-radius: var(--radius-md) var(--radius-md) 0 0; margin: calc(-1 * var(--spacing-xl)) calc(-1 * var(--spacing-xl)) 0 calc(-1 * var(--spacing-xl)); display: flex; align-items: center; justify-content: space-between; flex-shrink: 0;">
-                <h2 style="margin: 0; font-size: var(--font-size-base); font-weight: var(--font-weight-semibold); color: #ffffff;">"OZON FBS Posting Details"</h2>
-                <button
-                    class="button--ghost"
-                    on:click=move |_| on_close.run(())
-                >
-                    "✕ Закрыть"
-                </button>
+                <div style="display: flex; gap: var(--spacing-sm); align-items: center;">
+                    <h2 style="margin: 0; font-size: var(--font-size-base); font-weight: var(--font-weight-semibold); color: #ffffff;">"OZON FBS Posting Details"</h2>
+                    <SandboxBadge is_sandbox=Signal::derive(move || posting.get().is_some_and(|p| p.is_sandbox)) />
+                </div>
+                <div style="display: flex; gap: var(--spacing-sm); align-items: center;">
+                    {move || {
+                        posting
+                            .get()
+                            .map(|p| p.marketplace_links)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|link| view! {
+                                <a
+                                    class="button--ghost"
+                                    href=link.url
+                                    target="_blank"
+                                    rel="noopener noreferrer"
+                                >
+                                    {link.title}
+                                </a>
+                            })
+                            .collect_view()
+                    }}
+                    <button
+                        class="button--ghost"
+                        on:click=move |_| on_close.run(())
+                    >
+                        "✕ Закрыть"
+                    </button>
+                </div>
             </div>
 
             <div style="flex: 1; overflow-y: auto; min-height: 0;">
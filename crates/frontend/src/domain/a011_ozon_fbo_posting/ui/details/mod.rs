@@ -1,5 +1,7 @@
 use crate::domain::a014_ozon_transactions::ui::details::OzonTransactionsDetail;
 use crate::shared::api_utils::api_base;
+use crate::shared::components::sandbox_badge::SandboxBadge;
+use contracts::shared::marketplace_links::MarketplaceLinkDto;
 use gloo_net::http::Request;
 use leptos::logging::log;
 use leptos::prelude::*;
@@ -16,6 +18,10 @@ pub struct OzonFboPostingDetailDto {
     pub state: StateDto,
     pub source_meta: SourceMetaDto,
     pub metadata: MetadataDto,
+    #[serde(default)]
+    pub is_sandbox: bool,
+    #[serde(default)]
+    pub marketplace_links: Vec<MarketplaceLinkDto>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -323,13 +329,36 @@ pub fn OzonFboPostingDetail(
     view! {
         <div class="posting-detail" style="padding: 20px; height: 100%; display: flex; flex-direction: column;">
             <div style="display: flex; justify-content: space-between; align-items: center; margin-bottom: 20px; flex-shrink: 0;">
-                <h2 style="margin: 0;">"OZON FBO Posting Details"</h2>
-                <button
-                    on:click=move |_| on_close.run(())
-                    style="padding: 8px 16px; background: #f44336; color: white; border: none; border-radius: 4px; cursor: pointer;"
-                >
-                    "✕ Close"
-                </button>
+                <div style="display: flex; gap: 8px; align-items: center;">
+                    <h2 style="margin: 0;">"OZON FBO Posting Details"</h2>
+                    <SandboxBadge is_sandbox=Signal::derive(move || posting.get().is_some_and(|p| p.is_sandbox)) />
+                </div>
+                <div style="display: flex; gap: 8px; align-items: center;">
+                    {move || {
+                        posting
+                            .get()
+                            .map(|p| p.marketplace_links)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|link| view! {
+                                <a
+                                    href=link.url
+                                    target="_blank"
+                                    rel="noopener noreferrer"
+                                    style="padding: 8px 16px; border: 1px solid #005bff; color: #005bff; border-radius: 4px; text-decoration: none;"
+                                >
+                                    {link.title}
+                                </a>
+                            })
+                            .collect_view()
+                    }}
+                    <button
+                        on:click=move |_| on_close.run(())
+                        style="padding: 8px 16px; background: #f44336; color: white; border: none; border-radius: 4px; cursor: pointer;"
+                    >
+                        "✕ Close"
+                    </button>
+                </div>
             </div>
 
             <div style="flex: 1; overflow-y: auto; min-height: 0;">
@@ -0,0 +1,186 @@
+//! Сравнение 2–5 продаж WB колонками (`GET /api/a012/wb-sales/compare`).
+//!
+//! Открывается из списка по выбранным строкам; ключ таба —
+//! `a012_wb_sales_compare_<id>,<id>,...`. Строки с расхождениями подсвечиваются,
+//! переключатель оставляет только их.
+
+use crate::layout::global_context::AppGlobalContext;
+use crate::shared::api_client;
+use crate::shared::components::marketplace_links::MarketplaceLinkButtons;
+use crate::shared::date_utils::format_datetime_utc_local;
+use crate::shared::icons::icon;
+use crate::shared::money_format::format_money;
+use crate::shared::page_frame::PageFrame;
+use contracts::domain::a012_wb_sales::compare::{
+    CompareValueKind, WbSalesCompareResponse, WbSalesCompareRow,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::*;
+
+fn format_value(kind: CompareValueKind, value: Option<String>) -> String {
+    let Some(value) = value else {
+        return "—".to_string();
+    };
+    match kind {
+        CompareValueKind::Text => value,
+        CompareValueKind::Money => value.parse::<f64>().map(format_money).unwrap_or(value),
+        CompareValueKind::DateTime => format_datetime_utc_local(&value, "%d.%m.%Y %H:%M"),
+    }
+}
+
+#[component]
+pub fn WbSalesCompare(
+    /// id документов через запятую
+    ids: String,
+    #[prop(into)] on_close: Callback<()>,
+) -> impl IntoView {
+    let tabs_store =
+        leptos::context::use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
+    let data = RwSignal::new(None::<WbSalesCompareResponse>);
+    let error = RwSignal::new(None::<String>);
+    let only_differences = RwSignal::new(false);
+
+    spawn_local(async move {
+        let path = format!(
+            "/api/a012/wb-sales/compare?ids={}",
+            urlencoding::encode(&ids)
+        );
+        match api_client::get_json::<WbSalesCompareResponse>(&path).await {
+            Ok(response) => data.set(Some(response)),
+            Err(e) => error.set(Some(e.to_string())),
+        }
+    });
+
+    let open_detail = move |id: String, document_no: String| {
+        use crate::layout::tabs::{detail_tab_label, pick_identifier};
+        use contracts::domain::a012_wb_sales::ENTITY_METADATA as A012;
+        let identifier = pick_identifier(Some(&document_no), None, None, &id);
+        tabs_store.open_tab(
+            &format!("a012_wb_sales_details_{}", id),
+            &detail_tab_label(A012.ui.element_name, identifier),
+        );
+    };
+
+    view! {
+        <PageFrame page_id="a012_wb_sales--compare" category="detail">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Сравнение продаж WB"</h1>
+                </div>
+                <div class="page__header-right">
+                    <Switch checked=only_differences label="Только различия" />
+                    <Button
+                        appearance=ButtonAppearance::Subtle
+                        size=ButtonSize::Medium
+                        on_click=move |_| on_close.run(())
+                    >
+                        <span class="page-action-button__content">
+                            <span class="page-action-button__icon page-action-button__icon--close">{icon("x")}</span>
+                            <span class="page-action-button__text">"Закрыть"</span>
+                        </span>
+                    </Button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div style="padding: var(--spacing-lg); background: var(--color-error-50); border: 1px solid var(--color-error-100); border-radius: var(--radius-sm); color: var(--color-error);">
+                        <strong>"Ошибка: "</strong>{err}
+                    </div>
+                })}
+                {move || match data.get() {
+                    None if error.get().is_none() => view! {
+                        <Flex gap=FlexGap::Small style="align-items: center; padding: var(--spacing-4xl); justify-content: center;">
+                            <Spinner />
+                            <span>"Загрузка..."</span>
+                        </Flex>
+                    }.into_any(),
+                    None => view! { <></> }.into_any(),
+                    Some(response) => {
+                        let only_diff = only_differences.get();
+                        let rows: Vec<WbSalesCompareRow> = response
+                            .rows
+                            .into_iter()
+                            .filter(|row| !only_diff || row.differs)
+                            .collect();
+                        let mut last_group = String::new();
+                        let column_count = response.columns.len() + 1;
+                        view! {
+                            <Table attr:style="width: 100%; table-layout: fixed;">
+                                <TableHeader>
+                                    <TableRow>
+                                        <TableHeaderCell attr:style="width: 220px;">"Поле"</TableHeaderCell>
+                                        {response.columns.into_iter().map(|column| {
+                                            let id = column.id.clone();
+                                            let document_no = column.document_no.clone();
+                                            let links = Signal::derive({
+                                                let links = column.marketplace_links.clone();
+                                                move || links.clone()
+                                            });
+                                            view! {
+                                                <TableHeaderCell>
+                                                    <Flex vertical=true gap=FlexGap::Small>
+                                                        <a
+                                                            href="#"
+                                                            on:click=move |ev| {
+                                                                ev.prevent_default();
+                                                                open_detail(id.clone(), document_no.clone());
+                                                            }
+                                                        >
+                                                            {column.document_no.clone()}
+                                                        </a>
+                                                        <Badge
+                                                            appearance=BadgeAppearance::Filled
+                                                            color=if column.is_posted { BadgeColor::Success } else { BadgeColor::Warning }
+                                                        >
+                                                            {if column.is_posted { "Проведен" } else { "Не проведен" }}
+                                                        </Badge>
+                                                        <MarketplaceLinkButtons links=links />
+                                                    </Flex>
+                                                </TableHeaderCell>
+                                            }
+                                        }).collect_view()}
+                                    </TableRow>
+                                </TableHeader>
+                                <TableBody>
+                                    {rows.into_iter().map(|row| {
+                                        let group_header = (row.group != last_group).then(|| {
+                                            last_group = row.group.clone();
+                                            view! {
+                                                <TableRow>
+                                                    <TableCell attr:colspan=column_count>
+                                                        <strong>{row.group.clone()}</strong>
+                                                    </TableCell>
+                                                </TableRow>
+                                            }
+                                        });
+                                        let style = if row.differs {
+                                            "background: var(--color-warning-50);"
+                                        } else {
+                                            ""
+                                        };
+                                        let kind = row.kind;
+                                        view! {
+                                            {group_header}
+                                            <TableRow attr:style=style>
+                                                <TableCell>
+                                                    <TableCellLayout>{row.label}</TableCellLayout>
+                                                </TableCell>
+                                                {row.values.into_iter().map(|value| view! {
+                                                    <TableCell>
+                                                        <TableCellLayout>{format_value(kind, value)}</TableCellLayout>
+                                                    </TableCell>
+                                                }).collect_view()}
+                                            </TableRow>
+                                        }
+                                    }).collect_view()}
+                                </TableBody>
+                            </Table>
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </PageFrame>
+    }
+}
@@ -4,10 +4,12 @@
 //! - model.rs: DTOs and API functions
 //! - view_model.rs: WbSalesDetailsVm with RwSignals
 //! - page.rs: Main component with Header, TabBar, TabContent
+//! - post_preview.rs: confirmation dialog with p900/p904 impact before posting
 //! - tabs/: Tab components (general, line, json, links, projections)
 
 mod model;
 mod page;
+mod post_preview;
 mod tabs;
 mod view_model;
 
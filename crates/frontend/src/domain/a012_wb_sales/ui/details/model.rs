@@ -3,10 +3,15 @@
 //! Contains DTOs and async API functions for fetching and mutating WB Sales data.
 
 use crate::general_ledger::api::fetch_document_general_ledger_entries;
-use crate::shared::api_utils::api_base;
+use crate::shared::api_client::{self, ApiError};
+use contracts::domain::a012_wb_sales::finance_links::{
+    WbSalesFinanceLinksRequest, WbSalesFinanceLinksResponse,
+};
 use contracts::general_ledger::GeneralLedgerEntryDto;
-use contracts::projections::p903_wb_finance_report::dto::WbFinanceReportDto;
-use gloo_net::http::Request;
+use contracts::projections::p903_wb_finance_report::dto::{
+    WbFinanceReportDto, WbFinanceReportSridTotals,
+};
+use contracts::shared::marketplace_links::MarketplaceLinkDto;
 use serde::{Deserialize, Serialize};
 
 // ============================================
@@ -24,6 +29,8 @@ pub struct WbSalesDetailDto {
     pub warehouse: WarehouseDto,
     pub source_meta: SourceMetaDto,
     pub metadata: MetadataDto,
+    #[serde(default)]
+    pub is_sandbox: bool,
     pub marketplace_product_ref: Option<String>,
     pub nomenclature_ref: Option<String>,
     #[serde(default)]
@@ -36,6 +43,8 @@ pub struct WbSalesDetailDto {
     pub prod_cost_problem_message: Option<String>,
     #[serde(default)]
     pub prod_cost_resolved_total: Option<f64>,
+    #[serde(default)]
+    pub marketplace_links: Vec<MarketplaceLinkDto>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -105,6 +114,9 @@ pub struct SourceMetaDto {
     pub raw_payload_ref: String,
     pub fetched_at: String,
     pub document_version: i32,
+    /// Сессия импорта u504 — ключ раздела «Происхождение импорта»
+    #[serde(default)]
+    pub import_session_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -147,285 +159,159 @@ pub struct MarketplaceInfo {
 // API Functions
 // ============================================
 
+/// Строковое поле из JSON справочника; отсутствующее — пустая строка.
+fn str_field(json: &serde_json::Value, key: &str) -> String {
+    json.get(key)
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string()
+}
+
 /// Fetch WB Sales detail by ID
 pub async fn fetch_by_id(id: &str) -> Result<WbSalesDetailDto, String> {
-    let url = format!("{}/api/a012/wb-sales/{}", api_base(), id);
-
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
-
-    serde_json::from_str(&text).map_err(|e| format!("Failed to parse: {}", e))
+    Ok(api_client::get_json(&format!("/api/a012/wb-sales/{}", id)).await?)
 }
 
 /// Fetch raw JSON from WB API
 pub async fn fetch_raw_json(raw_payload_ref: &str) -> Result<String, String> {
-    let url = format!("{}/api/a012/raw/{}", api_base(), raw_payload_ref);
-
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch raw JSON: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
-
-    // Parse and pretty-print JSON
     let json_value: serde_json::Value =
-        serde_json::from_str(&text).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+        api_client::get_json(&format!("/api/a012/raw/{}", raw_payload_ref)).await?;
 
     serde_json::to_string_pretty(&json_value).map_err(|e| format!("Failed to format JSON: {}", e))
 }
 
 /// Fetch projections for a WB Sales document (p900, p904, p913 expense)
 pub async fn fetch_projections(id: &str) -> Result<serde_json::Value, String> {
-    let url = format!("{}/api/a012/wb-sales/{}/projections", api_base(), id);
-
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch projections: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
-
-    serde_json::from_str(&text).map_err(|e| format!("Failed to parse projections: {}", e))
+    Ok(api_client::get_json(&format!("/api/a012/wb-sales/{}/projections", id)).await?)
 }
 
 /// Fetch linked finance reports by SRID
 pub async fn fetch_finance_reports(srid: &str) -> Result<Vec<WbFinanceReportDto>, String> {
-    let url = format!(
-        "{}/api/p903/finance-report/search-by-srid?srid={}",
-        api_base(),
+    Ok(api_client::get_json(&format!(
+        "/api/p903/finance-report/search-by-srid?srid={}",
         srid
-    );
-
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch finance reports: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
+    ))
+    .await?)
+}
 
-    response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse finance reports: {}", e))
+/// Fetch finance report totals by SRID (summed on the backend)
+pub async fn fetch_finance_reports_totals(srid: &str) -> Result<WbFinanceReportSridTotals, String> {
+    Ok(api_client::get_json(&format!(
+        "/api/p903/finance-report/search-by-srid/totals?srid={}",
+        srid
+    ))
+    .await?)
 }
 
-/// Fetch marketplace product info
-pub async fn fetch_marketplace_product(id: &str) -> Result<MarketplaceProductInfo, String> {
-    let url = format!("{}/api/marketplace_product/{}", api_base(), id);
+/// Строки p903, привязанные к продаже вручную
+pub async fn fetch_finance_links(sale_id: &str) -> Result<WbSalesFinanceLinksResponse, String> {
+    Ok(api_client::get_json(&format!("/api/a012/wb-sales/{}/finance-links", sale_id)).await?)
+}
 
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch marketplace product: {}", e))?;
+/// Кандидаты на ручную привязку: тот же nm_id около даты продажи, SRID не совпал
+pub async fn fetch_finance_link_candidates(
+    sale_id: &str,
+) -> Result<Vec<WbFinanceReportDto>, String> {
+    Ok(api_client::get_json(&format!(
+        "/api/a012/wb-sales/{}/finance-links/candidates",
+        sale_id
+    ))
+    .await?)
+}
 
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
+pub async fn attach_finance_links(
+    sale_id: &str,
+    rrd_ids: Vec<i64>,
+) -> Result<WbSalesFinanceLinksResponse, String> {
+    Ok(api_client::post_json(
+        &format!("/api/a012/wb-sales/{}/finance-links", sale_id),
+        &WbSalesFinanceLinksRequest { rrd_ids },
+    )
+    .await?)
+}
 
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
+pub async fn detach_finance_links(
+    sale_id: &str,
+    rrd_ids: Vec<i64>,
+) -> Result<WbSalesFinanceLinksResponse, String> {
+    Ok(api_client::delete_json(
+        &format!("/api/a012/wb-sales/{}/finance-links", sale_id),
+        &WbSalesFinanceLinksRequest { rrd_ids },
+    )
+    .await?)
+}
 
+/// Fetch marketplace product info
+pub async fn fetch_marketplace_product(id: &str) -> Result<MarketplaceProductInfo, String> {
     let json: serde_json::Value =
-        serde_json::from_str(&text).map_err(|e| format!("Failed to parse: {}", e))?;
+        api_client::get_json(&format!("/api/marketplace_product/{}", id)).await?;
 
     Ok(MarketplaceProductInfo {
-        description: json
-            .get("description")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
-        article: json
-            .get("article")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
+        description: str_field(&json, "description"),
+        article: str_field(&json, "article"),
     })
 }
 
 /// Fetch nomenclature info
 pub async fn fetch_nomenclature(id: &str) -> Result<NomenclatureInfo, String> {
-    let url = format!("{}/api/nomenclature/{}", api_base(), id);
-
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch nomenclature: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
-
     let json: serde_json::Value =
-        serde_json::from_str(&text).map_err(|e| format!("Failed to parse: {}", e))?;
+        api_client::get_json(&format!("/api/nomenclature/{}", id)).await?;
 
     Ok(NomenclatureInfo {
-        description: json
-            .get("description")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
-        article: json
-            .get("article")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
+        description: str_field(&json, "description"),
+        article: str_field(&json, "article"),
     })
 }
 
-/// Post (проведение) document
-pub async fn post_document(id: &str) -> Result<(), String> {
-    let url = format!("{}/api/a012/wb-sales/{}/post", api_base(), id);
-
-    let response = Request::post(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to post document: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Failed to post: status {}", response.status()));
-    }
-
-    Ok(())
+/// Fetch posting preview: p900/p904 rows now (`current`) and after posting (`preview`)
+pub async fn fetch_post_preview(id: &str) -> Result<serde_json::Value, String> {
+    Ok(api_client::get_json(&format!("/api/a012/wb-sales/{}/post-preview", id)).await?)
 }
 
-/// Unpost (отмена проведения) document
-pub async fn unpost_document(id: &str) -> Result<(), String> {
-    let url = format!("{}/api/a012/wb-sales/{}/unpost", api_base(), id);
-
-    let response = Request::post(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to unpost document: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Failed to unpost: status {}", response.status()));
-    }
+/// Post (проведение) document; `version` — версия на экране (409 при конфликте)
+pub async fn post_document(id: &str, version: Option<i32>) -> Result<(), ApiError> {
+    api_client::post_empty(&api_client::with_version(
+        &format!("/api/a012/wb-sales/{}/post", id),
+        version,
+    ))
+    .await
+}
 
-    Ok(())
+/// Unpost (отмена проведения) document; `version` — версия на экране (409 при конфликте)
+pub async fn unpost_document(id: &str, version: Option<i32>) -> Result<(), ApiError> {
+    api_client::post_empty(&api_client::with_version(
+        &format!("/api/a012/wb-sales/{}/unpost", id),
+        version,
+    ))
+    .await
 }
 
 /// Fetch connection info
 pub async fn fetch_connection(id: &str) -> Result<ConnectionInfo, String> {
-    let url = format!("{}/api/connection_mp/{}", api_base(), id);
-
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch connection: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
-
     let json: serde_json::Value =
-        serde_json::from_str(&text).map_err(|e| format!("Failed to parse: {}", e))?;
+        api_client::get_json(&format!("/api/connection_mp/{}", id)).await?;
 
     Ok(ConnectionInfo {
-        description: json
-            .get("description")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
+        description: str_field(&json, "description"),
     })
 }
 
 /// Fetch organization info
 pub async fn fetch_organization(id: &str) -> Result<OrganizationInfo, String> {
-    let url = format!("{}/api/organization/{}", api_base(), id);
-
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch organization: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
-
     let json: serde_json::Value =
-        serde_json::from_str(&text).map_err(|e| format!("Failed to parse: {}", e))?;
+        api_client::get_json(&format!("/api/organization/{}", id)).await?;
 
     Ok(OrganizationInfo {
-        description: json
-            .get("description")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
+        description: str_field(&json, "description"),
     })
 }
 
 /// Fetch marketplace info
 pub async fn fetch_marketplace(id: &str) -> Result<MarketplaceInfo, String> {
-    let url = format!("{}/api/marketplace/{}", api_base(), id);
-
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch marketplace: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
-
-    let json: serde_json::Value =
-        serde_json::from_str(&text).map_err(|e| format!("Failed to parse: {}", e))?;
+    let json: serde_json::Value = api_client::get_json(&format!("/api/marketplace/{}", id)).await?;
 
     Ok(MarketplaceInfo {
-        name: json
-            .get("description")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
+        name: str_field(&json, "description"),
     })
 }
 
@@ -483,56 +369,21 @@ struct OrderIdDto {
 }
 
 pub async fn resolve_order_uuid_by_srid(srid: &str) -> Option<String> {
-    let url = format!(
-        "{}/api/a015/wb-orders/search-by-srid?srid={}",
-        api_base(),
+    let orders: Vec<OrderIdDto> = api_client::get_json(&format!(
+        "/api/a015/wb-orders/search-by-srid?srid={}",
         urlencoding::encode(srid)
-    );
-    let response = Request::get(&url).send().await.ok()?;
-    if !response.ok() {
-        return None;
-    }
-    let orders: Vec<OrderIdDto> = response.json().await.ok()?;
+    ))
+    .await
+    .ok()?;
     orders.into_iter().next().map(|o| o.id)
 }
 
 /// Fetch advert attribution for a WB Sales document (расшифровка advert_clicks_order_expense)
 pub async fn fetch_advert_attribution(id: &str) -> Result<AdvertAttributionResponse, String> {
-    let url = format!("{}/api/a012/wb-sales/{}/advert-attribution", api_base(), id);
-
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch advert attribution: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
-
-    serde_json::from_str(&text).map_err(|e| format!("Failed to parse: {}", e))
+    Ok(api_client::get_json(&format!("/api/a012/wb-sales/{}/advert-attribution", id)).await?)
 }
 
 /// Refresh dealer price
 pub async fn refresh_dealer_price(id: &str) -> Result<(), String> {
-    let url = format!(
-        "{}/api/a012/wb-sales/{}/refresh-dealer-price",
-        api_base(),
-        id
-    );
-
-    let response = Request::post(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to refresh dealer price: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Failed to refresh: status {}", response.status()));
-    }
-
-    Ok(())
+    Ok(api_client::post_empty(&format!("/api/a012/wb-sales/{}/refresh-dealer-price", id)).await?)
 }
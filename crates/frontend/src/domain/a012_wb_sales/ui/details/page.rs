@@ -7,16 +7,24 @@
 //! - Routes to tab components
 //! - Handles lazy loading for nested data
 
+use super::post_preview::PostPreviewDialog;
 use super::tabs::{
     AdvertAttributionTab, GeneralTab, JournalTab, JsonTab, LineTab, LinksTab, PlanFactTab,
     ProjectionsTab,
 };
 use super::view_model::WbSalesDetailsVm;
 use crate::layout::global_context::AppGlobalContext;
+use crate::layout::tab_session;
+use crate::shared::components::marketplace_links::MarketplaceLinkButtons;
 use crate::shared::components::more_actions_menu::{use_more_actions_close, MoreActionsMenu};
+use crate::shared::components::sandbox_badge::SandboxBadge;
+use crate::shared::components::version_conflict_dialog::VersionConflictDialog;
 use crate::shared::icons::icon;
 use crate::shared::page_frame::PageFrame;
+use crate::system::document_audit::ui::DocumentHistoryPanel;
+use crate::system::external_refs::ui::ExternalRefsPanel;
 use crate::system::favorites::ui::FavoriteButton;
+use crate::system::scheduled_posts::ui::ScheduledPostControl;
 use leptos::prelude::*;
 use thaw::*;
 
@@ -29,6 +37,22 @@ pub fn WbSalesDetail(id: String, #[prop(into)] on_close: Callback<()>) -> impl I
     let stored_id = StoredValue::new(id.clone());
 
     vm.load(id);
+    tab_session::bind_sub_tab(
+        format!("a012_wb_sales_details_{}", stored_id.get_value()),
+        vm.active_tab,
+        &[
+            "general",
+            "planfact",
+            "line",
+            "json",
+            "links",
+            "advert_attribution",
+            "projections",
+            "journal",
+            "external_refs",
+            "history",
+        ],
+    );
 
     Effect::new({
         let vm = vm.clone();
@@ -66,9 +90,18 @@ pub fn WbSalesDetail(id: String, #[prop(into)] on_close: Callback<()>) -> impl I
     let vm_tabs = vm.clone();
     let vm_warning = vm.clone();
     let vm_content = vm.clone();
+    let vm_preview = vm.clone();
+    let version_conflict = vm.version_conflict;
+    let on_conflict_reload = {
+        let vm = vm.clone();
+        Callback::new(move |_: ()| vm.reload_document())
+    };
 
     view! {
         <PageFrame page_id="a012_wb_sales--detail" category="detail">
+            <PostPreviewDialog vm=vm_preview />
+            <VersionConflictDialog conflict=version_conflict on_reload=on_conflict_reload />
+
             <Header
                 vm=vm_header
                 favorite_target_id=stored_id.get_value()
@@ -115,9 +148,12 @@ fn Header(
     on_close: Callback<()>,
 ) -> impl IntoView {
     let is_posted = vm.is_posted();
+    let is_sandbox = vm.is_sandbox();
     let sale_id = vm.sale_id();
     let title = Signal::derive(move || format!("WB Sales {}", sale_id.get()));
     let sale = vm.sale;
+    let marketplace_links =
+        Signal::derive(move || sale.get().map(|s| s.marketplace_links).unwrap_or_default());
     let tab_key = format!("a012_wb_sales_details_{}", favorite_target_id);
 
     // Поднятое состояние меню «Ещё», чтобы открывать его и кнопкой, и правым кликом по заголовку.
@@ -161,8 +197,11 @@ fn Header(
                         }
                     }}
                 </Show>
+                <SandboxBadge is_sandbox=is_sandbox />
             </div>
             <div class="page__header-right">
+                <MarketplaceLinkButtons links=marketplace_links />
+                <ScheduledPostControl entity_type="a012_wb_sales" document_id=vm.id />
                 <PostButtons vm=vm.clone() more_open=more_open more_pos=more_pos />
                 <Button
                     appearance=ButtonAppearance::Subtle
@@ -193,7 +232,8 @@ fn PostButtons(
 
     let on_post = {
         let vm = vm.clone();
-        Callback::new(move |_: ()| vm.post())
+        // Проведение — через диалог с итогами p900/p904 до и после.
+        Callback::new(move |_: ()| vm.open_post_preview())
     };
     let on_unpost = {
         let vm = vm;
@@ -387,7 +427,10 @@ fn TabBar(vm: WbSalesDetailsVm) -> impl IntoView {
             <button
                 class="page__tab"
                 class:page__tab--active=move || active_tab.get() == "journal"
-                on:click=move |_| vm.set_tab("journal")
+                on:click={
+                    let vm = vm.clone();
+                    move |_| vm.set_tab("journal")
+                }
             >
                 {icon("book-open")} "Журнал"
                 <Badge
@@ -405,6 +448,22 @@ fn TabBar(vm: WbSalesDetailsVm) -> impl IntoView {
                     {move || general_ledger_entries_count.get().to_string()}
                 </Badge>
             </button>
+
+            <button
+                class="page__tab"
+                class:page__tab--active=move || active_tab.get() == "external_refs"
+                on:click=move |_| vm.set_tab("external_refs")
+            >
+                {icon("link")} "Внешние ссылки"
+            </button>
+
+            <button
+                class="page__tab"
+                class:page__tab--active=move || active_tab.get() == "history"
+                on:click=move |_| vm.set_tab("history")
+            >
+                {icon("clock")} "История"
+            </button>
         </div>
     }
 }
@@ -439,6 +498,7 @@ fn TabContent(vm: WbSalesDetailsVm) -> impl IntoView {
     let vm_projections = vm.clone();
     let vm_journal = vm.clone();
     let vm_attribution = vm.clone();
+    let document_id = vm.id;
 
     view! {
         {move || match active_tab.get() {
@@ -450,6 +510,8 @@ fn TabContent(vm: WbSalesDetailsVm) -> impl IntoView {
             "advert_attribution" => view! { <AdvertAttributionTab vm=vm_attribution.clone() /> }.into_any(),
             "projections"        => view! { <ProjectionsTab       vm=vm_projections.clone() /> }.into_any(),
             "journal"            => view! { <JournalTab           vm=vm_journal.clone()     /> }.into_any(),
+            "external_refs"      => view! { <ExternalRefsPanel entity_type="a012_wb_sales" document_id=document_id /> }.into_any(),
+            "history"            => view! { <DocumentHistoryPanel entity_type="a012_wb_sales" document_id=document_id /> }.into_any(),
             _                    => view! { <GeneralTab           vm=vm_general.clone()     /> }.into_any(),
         }}
     }
@@ -0,0 +1,174 @@
+//! Posting preview dialog - what posting will change in p900/p904
+
+use super::view_model::WbSalesDetailsVm;
+use crate::shared::money_format::{amount_class, format_money, format_qty};
+use leptos::prelude::*;
+use thaw::*;
+
+/// Одна строка сравнения «сейчас → после проведения».
+#[derive(Clone)]
+struct ImpactRow {
+    label: &'static str,
+    current: f64,
+    preview: f64,
+    is_qty: bool,
+}
+
+fn rows<'a>(data: &'a serde_json::Value, section: &str, table: &str) -> &'a [serde_json::Value] {
+    data[section][table]
+        .as_array()
+        .map(|a| a.as_slice())
+        .unwrap_or(&[])
+}
+
+fn sum(data: &serde_json::Value, section: &str, table: &str, field: &str) -> f64 {
+    rows(data, section, table)
+        .iter()
+        .filter_map(|row| row[field].as_f64())
+        .sum()
+}
+
+fn impact_rows(data: &serde_json::Value) -> Vec<ImpactRow> {
+    let count = |label, table| ImpactRow {
+        label,
+        current: rows(data, "current", table).len() as f64,
+        preview: rows(data, "preview", table).len() as f64,
+        is_qty: true,
+    };
+    let total = |label, table, field, is_qty| ImpactRow {
+        label,
+        current: sum(data, "current", table, field),
+        preview: sum(data, "preview", table, field),
+        is_qty,
+    };
+
+    const P900: &str = "p900_sales_register";
+    const P904: &str = "p904_sales_data";
+    vec![
+        count("p900: строк", P900),
+        total("p900: количество", P900, "qty", true),
+        total("p900: сумма строки", P900, "amount_line", false),
+        total("p900: себестоимость", P900, "cost", false),
+        count("p904: строк", P904),
+        total("p904: от покупателя", P904, "customer_in", false),
+        total("p904: покупателю", P904, "customer_out", false),
+        total("p904: комиссия", P904, "commission_out", false),
+        total("p904: эквайринг", P904, "acquiring_out", false),
+        total("p904: логистика", P904, "logistics_out", false),
+        total("p904: продавцу", P904, "seller_out", false),
+        total("p904: итого", P904, "total", false),
+        total("p904: себестоимость", P904, "cost", false),
+    ]
+}
+
+fn format_value(value: f64, is_qty: bool) -> String {
+    if is_qty {
+        format_qty(value)
+    } else {
+        format_money(value)
+    }
+}
+
+/// Диалог подтверждения проведения: итоги p900/p904 до и после, без записи в БД.
+#[component]
+pub fn PostPreviewDialog(vm: WbSalesDetailsVm) -> impl IntoView {
+    let open = vm.post_preview_open;
+    let preview = vm.post_preview;
+    let loading = vm.post_preview_loading;
+    let error = vm.post_preview_error;
+    let is_posted = vm.is_posted();
+
+    let on_confirm = {
+        let vm = vm.clone();
+        move |_| vm.confirm_post()
+    };
+
+    view! {
+        <Dialog open=open>
+            <DialogSurface>
+                <DialogBody>
+                    <DialogTitle>
+                        {move || if is_posted.get() {
+                            "Перепроведение: что изменится"
+                        } else {
+                            "Проведение: что изменится"
+                        }}
+                    </DialogTitle>
+                    <DialogContent>
+                        {move || {
+                            if loading.get() {
+                                return view! {
+                                    <Flex gap=FlexGap::Small style="align-items: center; padding: var(--spacing-lg);">
+                                        <Spinner />
+                                        <span>"Расчёт проекций..."</span>
+                                    </Flex>
+                                }.into_any();
+                            }
+                            if let Some(err) = error.get() {
+                                return view! {
+                                    <div style="color: var(--color-error);">
+                                        {format!("Не удалось рассчитать предпросмотр: {}", err)}
+                                    </div>
+                                }.into_any();
+                            }
+                            let Some(data) = preview.get() else {
+                                return view! { <div></div> }.into_any();
+                            };
+                            let impact = impact_rows(&data);
+
+                            view! {
+                                <div style="overflow-x: auto;">
+                                    <Table>
+                                        <TableHeader>
+                                            <TableRow>
+                                                <TableHeaderCell>"Показатель"</TableHeaderCell>
+                                                <TableHeaderCell attr:style="text-align: right;">"Сейчас"</TableHeaderCell>
+                                                <TableHeaderCell attr:style="text-align: right;">"После"</TableHeaderCell>
+                                                <TableHeaderCell attr:style="text-align: right;">"Изменение"</TableHeaderCell>
+                                            </TableRow>
+                                        </TableHeader>
+                                        <TableBody>
+                                            {impact
+                                                .into_iter()
+                                                .map(|row| {
+                                                    let diff = row.preview - row.current;
+                                                    view! {
+                                                        <TableRow>
+                                                            <TableCell><TableCellLayout>{row.label}</TableCellLayout></TableCell>
+                                                            <TableCell attr:style="text-align: right;"><TableCellLayout>{format_value(row.current, row.is_qty)}</TableCellLayout></TableCell>
+                                                            <TableCell attr:style="text-align: right;"><TableCellLayout>{format_value(row.preview, row.is_qty)}</TableCellLayout></TableCell>
+                                                            <TableCell attr:style="text-align: right;">
+                                                                <TableCellLayout>
+                                                                    <span class=amount_class(diff)>{format_value(diff, row.is_qty)}</span>
+                                                                </TableCellLayout>
+                                                            </TableCell>
+                                                        </TableRow>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </TableBody>
+                                    </Table>
+                                </div>
+                                <div style="margin-top: var(--spacing-sm); color: var(--color-text-secondary); font-size: var(--font-size-sm);">
+                                    "Журнал операций, p909, p913 и p916 также будут пересобраны."
+                                </div>
+                            }.into_any()
+                        }}
+                    </DialogContent>
+                    <DialogActions>
+                        <Button appearance=ButtonAppearance::Secondary on_click=move |_| open.set(false)>
+                            "Отмена"
+                        </Button>
+                        <Button
+                            appearance=ButtonAppearance::Primary
+                            on_click=on_confirm
+                            disabled=Signal::derive(move || loading.get())
+                        >
+                            {move || if is_posted.get() { "Перепровести" } else { "Провести" }}
+                        </Button>
+                    </DialogActions>
+                </DialogBody>
+            </DialogSurface>
+        </Dialog>
+    }
+}
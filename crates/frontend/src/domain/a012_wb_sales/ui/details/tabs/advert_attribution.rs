@@ -9,17 +9,10 @@
 use super::super::view_model::WbSalesDetailsVm;
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::components::card_animated::CardAnimated;
+use crate::shared::money_format::{format_money, format_money_rub, format_number};
 use leptos::prelude::*;
 use thaw::*;
 
-fn fmt_money(v: f64) -> String {
-    format!("{:.2}", v)
-}
-
-fn fmt_ratio(v: f64) -> String {
-    format!("{:.2}", v)
-}
-
 fn fmt_date(v: &str) -> String {
     if let Some((y, rest)) = v.split_once('-') {
         if let Some((m, d)) = rest.split_once('-') {
@@ -93,12 +86,12 @@ pub fn AdvertAttributionTab(vm: WbSalesDetailsVm) -> impl IntoView {
                             {format!("Кампаний: {}", campaigns_count)}
                         </Badge>
                         <Badge appearance=BadgeAppearance::Tint color=BadgeColor::Brand>
-                            {format!("Сумма атрибуции: {} ₽", fmt_money(total_sum))}
+                            {format!("Сумма атрибуции: {}", format_money_rub(total_sum))}
                         </Badge>
                         {match (is_posted, gl_amount) {
                             (true, Some(gl)) => view! {
                                 <Badge appearance=BadgeAppearance::Tint color=BadgeColor::Success>
-                                    {format!("advert_clicks_order_expense в журнале: {} ₽", fmt_money(gl))}
+                                    {format!("advert_clicks_order_expense в журнале: {}", format_money_rub(gl))}
                                 </Badge>
                             }.into_any(),
                             (true, None) => view! {
@@ -118,7 +111,7 @@ pub fn AdvertAttributionTab(vm: WbSalesDetailsVm) -> impl IntoView {
                                 let delta = total_sum - gl_amount.unwrap_or(0.0);
                                 view! {
                                     <Badge appearance=BadgeAppearance::Filled color=BadgeColor::Danger>
-                                        {format!("Расхождение: {} ₽", fmt_money(delta))}
+                                        {format!("Расхождение: {}", format_money_rub(delta))}
                                     </Badge>
                                 }.into_any()
                             }
@@ -182,8 +175,8 @@ pub fn AdvertAttributionTab(vm: WbSalesDetailsVm) -> impl IntoView {
                                         children=move |row| {
                                             let entry_date = fmt_date(&row.entry_date);
                                             let advert_id = row.advert_id.clone();
-                                            let amount = fmt_money(row.amount);
-                                            let ratio = fmt_ratio(row.ratio_percent);
+                                            let amount = format_money(row.amount);
+                                            let ratio = format_number(row.ratio_percent, 2);
 
                                             let article_text = row
                                                 .nomenclature_article
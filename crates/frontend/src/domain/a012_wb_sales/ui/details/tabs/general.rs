@@ -3,6 +3,7 @@
 use super::super::view_model::WbSalesDetailsVm;
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::components::card_animated::CardAnimated;
+use crate::shared::components::sku_sparkline::SkuSparkline;
 use crate::shared::date_utils::format_datetime_utc_local;
 use leptos::prelude::*;
 use thaw::*;
@@ -94,6 +95,8 @@ pub fn GeneralTab(vm: WbSalesDetailsVm) -> impl IntoView {
             let nom_ref = sale_data.nomenclature_ref.clone();
             let mp_ref_click = mp_ref.clone();
             let mp_ref_text = mp_ref.clone();
+            let mp_ref_sparkline = mp_ref.clone();
+            let sparkline_date = sale_data.state.sale_dt.clone();
             let nom_ref_click = nom_ref.clone();
             let nom_ref_text = nom_ref.clone();
 
@@ -176,6 +179,9 @@ pub fn GeneralTab(vm: WbSalesDetailsVm) -> impl IntoView {
                                             .unwrap_or_else(|| "Открыть".to_string())
                                     }}
                                 </a>
+                                {mp_ref_sparkline.map(|id| view! {
+                                    <SkuSparkline marketplace_product_ref=id date_to=sparkline_date />
+                                })}
                             </div>
                             <div class="form__group">
                                 <label class="form__label">"Номенклатура 1С"</label>
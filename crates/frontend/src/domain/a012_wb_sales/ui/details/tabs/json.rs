@@ -2,6 +2,8 @@
 
 use super::super::view_model::WbSalesDetailsVm;
 use crate::shared::components::card_animated::CardAnimated;
+use crate::shared::components::import_provenance::ImportProvenanceSection;
+use crate::shared::components::raw_payload_versions::RawPayloadVersions;
 use crate::shared::json_viewer::widget::JsonViewer;
 use leptos::prelude::*;
 use thaw::*;
@@ -9,6 +11,24 @@ use thaw::*;
 /// JSON tab component - displays raw JSON from WB API
 #[component]
 pub fn JsonTab(vm: WbSalesDetailsVm) -> impl IntoView {
+    let import_session_id = Signal::derive({
+        let vm = vm.clone();
+        move || {
+            vm.sale
+                .get()
+                .and_then(|sale| sale.source_meta.import_session_id)
+        }
+    });
+
+    let raw_payload_ref = Signal::derive({
+        let vm = vm.clone();
+        move || vm.sale.get().map(|sale| sale.source_meta.raw_payload_ref)
+    });
+    let raw_json = Signal::derive({
+        let vm = vm.clone();
+        move || vm.raw_json.get()
+    });
+
     view! {
         {move || {
             if vm.raw_json_loading.get() {
@@ -29,6 +49,11 @@ pub fn JsonTab(vm: WbSalesDetailsVm) -> impl IntoView {
                         nav_id="a012_wb_sales_details_json_main"
                         style="padding: var(--spacing-sm);"
                     >
+                        <RawPayloadVersions
+                            raw_base="/api/a012/raw"
+                            current_ref=raw_payload_ref
+                            current_json=raw_json
+                        />
                         <JsonViewer
                             json_content=json
                             title="Raw JSON from WB".to_string()
@@ -46,5 +71,10 @@ pub fn JsonTab(vm: WbSalesDetailsVm) -> impl IntoView {
                 }.into_any()
             }
         }}
+        {move || import_session_id.get().map(|session_id| view! {
+            <CardAnimated delay_ms=40 nav_id="a012_wb_sales_details_json_provenance">
+                <ImportProvenanceSection session_id=session_id aggregate_index="a012_wb_sales" />
+            </CardAnimated>
+        })}
     }
 }
@@ -3,6 +3,7 @@
 use super::super::view_model::WbSalesDetailsVm;
 use crate::shared::components::card_animated::CardAnimated;
 use crate::shared::components::table::TableCellMoney;
+use crate::shared::money_format::{format_money, format_money_opt, format_percent, format_qty};
 use leptos::prelude::*;
 use thaw::*;
 
@@ -24,7 +25,7 @@ pub fn LineTab(vm: WbSalesDetailsVm) -> impl IntoView {
             let nm_id = line.nm_id.to_string();
             let barcode = line.barcode.clone();
             let name = line.name.clone();
-            let qty = format!("{:.0}", line.qty);
+            let qty = format_qty(line.qty);
 
             // Amounts rows data
             let amounts_rows: Vec<(&'static str, &'static str, Option<f64>, &'static str)> = vec![
@@ -45,14 +46,14 @@ pub fn LineTab(vm: WbSalesDetailsVm) -> impl IntoView {
             for (idx, report) in finance_reports.iter().enumerate() {
                 let row_num = idx + 1;
                 finance_rows.push((row_num, "Дата операции".to_string(), "rr_dt".to_string(), report.rr_dt.clone()));
-                if let Some(v) = report.ppvz_vw { finance_rows.push((row_num, "Вознаграждение ВВ, без НДС".to_string(), "ppvz_vw".to_string(), format!("{:.2}", v))); }
-                if let Some(v) = report.ppvz_vw_nds { finance_rows.push((row_num, "НДС с вознаграждения ВВ".to_string(), "ppvz_vw_nds".to_string(), format!("{:.2}", v))); }
-                if let Some(v) = report.retail_amount { finance_rows.push((row_num, "WB реализовал товар".to_string(), "retail_amount".to_string(), format!("{:.2}", v))); }
-                if let Some(v) = report.ppvz_for_pay { finance_rows.push((row_num, "К перечислению продавцу".to_string(), "ppvz_for_pay".to_string(), format!("{:.2}", v))); }
-                if let Some(v) = report.commission_percent { finance_rows.push((row_num, "Размер кВВ, %".to_string(), "commission_percent".to_string(), format!("{:.2}", v))); }
-                if let Some(v) = report.retail_price { finance_rows.push((row_num, "Цена розничная".to_string(), "retail_price".to_string(), format!("{:.2}", v))); }
-                if let Some(v) = report.retail_price_withdisc_rub { finance_rows.push((row_num, "Цена с учетом скидки".to_string(), "retail_price_withdisc_rub".to_string(), format!("{:.2}", v))); }
-                if let Some(v) = report.acquiring_fee { finance_rows.push((row_num, "Эквайринг".to_string(), "acquiring_fee".to_string(), format!("{:.2}", v))); }
+                if let Some(v) = report.ppvz_vw { finance_rows.push((row_num, "Вознаграждение ВВ, без НДС".to_string(), "ppvz_vw".to_string(), format_money(v))); }
+                if let Some(v) = report.ppvz_vw_nds { finance_rows.push((row_num, "НДС с вознаграждения ВВ".to_string(), "ppvz_vw_nds".to_string(), format_money(v))); }
+                if let Some(v) = report.retail_amount { finance_rows.push((row_num, "WB реализовал товар".to_string(), "retail_amount".to_string(), format_money(v))); }
+                if let Some(v) = report.ppvz_for_pay { finance_rows.push((row_num, "К перечислению продавцу".to_string(), "ppvz_for_pay".to_string(), format_money(v))); }
+                if let Some(v) = report.commission_percent { finance_rows.push((row_num, "Размер кВВ".to_string(), "commission_percent".to_string(), format_percent(v, 2))); }
+                if let Some(v) = report.retail_price { finance_rows.push((row_num, "Цена розничная".to_string(), "retail_price".to_string(), format_money(v))); }
+                if let Some(v) = report.retail_price_withdisc_rub { finance_rows.push((row_num, "Цена с учетом скидки".to_string(), "retail_price_withdisc_rub".to_string(), format_money(v))); }
+                if let Some(v) = report.acquiring_fee { finance_rows.push((row_num, "Эквайринг".to_string(), "acquiring_fee".to_string(), format_money(v))); }
             }
             let has_finance_data = !finance_rows.is_empty();
 
@@ -91,9 +92,7 @@ pub fn LineTab(vm: WbSalesDetailsVm) -> impl IntoView {
                                 <Flex gap=FlexGap::Small style="align-items: center;">
                                     <Input
                                         value=RwSignal::new(
-                                            line.dealer_price_ut
-                                                .map(|p| format!("{:.2}", p))
-                                                .unwrap_or_else(|| "—".to_string())
+                                            format_money_opt(line.dealer_price_ut)
                                         )
                                         attr:readonly=true
                                         attr:style="flex: 1;"
@@ -1,10 +1,15 @@
-//! Links tab - linked finance reports
+//! Links tab - linked finance reports (by SRID and manually attached)
 
 use super::super::view_model::WbSalesDetailsVm;
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::components::card_animated::CardAnimated;
+use crate::shared::components::table::{TableCellCheckbox, TableHeaderCheckbox};
+use crate::shared::icons::icon;
+use crate::shared::money_format::{amount_class, format_money, format_money_opt, format_percent_opt};
+use crate::system::print_templates::ui::PrintDocumentPanel;
 use contracts::projections::p903_wb_finance_report::dto::WbFinanceReportDto;
 use leptos::prelude::*;
+use std::collections::HashSet;
 use thaw::*;
 
 /// Links tab component - displays linked finance reports
@@ -13,7 +18,11 @@ pub fn LinksTab(vm: WbSalesDetailsVm) -> impl IntoView {
     let tabs_store =
         leptos::context::use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
 
+    let manual_vm = vm.clone();
+    let document_id = vm.id;
+
     view! {
+        <PrintDocumentPanel entity_type="a012_wb_sales" document_id=document_id />
         {move || {
             if vm.finance_reports_loading.get() {
                 return view! {
@@ -48,13 +57,14 @@ pub fn LinksTab(vm: WbSalesDetailsVm) -> impl IntoView {
                 }.into_any();
             }
 
-            // Calculate totals
-            let reports_count = reports.len();
-            let total_ppvz_vw: f64 = reports.iter().filter_map(|r| r.ppvz_vw).sum();
-            let total_ppvz_vw_nds: f64 = reports.iter().filter_map(|r| r.ppvz_vw_nds).sum();
-            let total_retail: f64 = reports.iter().filter_map(|r| r.retail_amount).sum();
-            let total_ppvz_for_pay: f64 = reports.iter().filter_map(|r| r.ppvz_for_pay).sum();
-            let total_acquiring: f64 = reports.iter().filter_map(|r| r.acquiring_fee).sum();
+            // Totals are summed on the backend (p903 search-by-srid/totals)
+            let totals = vm.finance_reports_totals.get();
+            let reports_count = totals.count;
+            let total_ppvz_vw = totals.ppvz_vw;
+            let total_ppvz_vw_nds = totals.ppvz_vw_nds;
+            let total_retail = totals.retail_amount;
+            let total_ppvz_for_pay = totals.ppvz_for_pay;
+            let total_acquiring = totals.acquiring_fee;
 
             // Clone for use in For loop
             let reports_for_table = reports;
@@ -68,11 +78,11 @@ pub fn LinksTab(vm: WbSalesDetailsVm) -> impl IntoView {
                         <Badge appearance=BadgeAppearance::Tint color=BadgeColor::Brand>
                             {format!("Найдено: {}", reports_count)}
                         </Badge>
-                        <span>"PPVZ VW: " <strong>{format!("{:.2}", total_ppvz_vw)}</strong></span>
-                        <span>"PPVZ VW NDS: " <strong>{format!("{:.2}", total_ppvz_vw_nds)}</strong></span>
-                        <span>"Retail: " <strong>{format!("{:.2}", total_retail)}</strong></span>
-                        <span>"For Pay: " <strong>{format!("{:.2}", total_ppvz_for_pay)}</strong></span>
-                        <span>"Acquiring: " <strong>{format!("{:.2}", total_acquiring)}</strong></span>
+                        <span>"PPVZ VW: " <strong class=amount_class(total_ppvz_vw)>{format_money(total_ppvz_vw)}</strong></span>
+                        <span>"PPVZ VW NDS: " <strong class=amount_class(total_ppvz_vw_nds)>{format_money(total_ppvz_vw_nds)}</strong></span>
+                        <span>"Retail: " <strong class=amount_class(total_retail)>{format_money(total_retail)}</strong></span>
+                        <span>"For Pay: " <strong class=amount_class(total_ppvz_for_pay)>{format_money(total_ppvz_for_pay)}</strong></span>
+                        <span>"Acquiring: " <strong class=amount_class(total_acquiring)>{format_money(total_acquiring)}</strong></span>
                     </Flex>
 
                     // Table
@@ -86,7 +96,7 @@ pub fn LinksTab(vm: WbSalesDetailsVm) -> impl IntoView {
                                     <TableHeaderCell>"PPVZ VW NDS"</TableHeaderCell>
                                     <TableHeaderCell>"Retail Amount"</TableHeaderCell>
                                     <TableHeaderCell>"PPVZ For Pay"</TableHeaderCell>
-                                    <TableHeaderCell>"Commission %"</TableHeaderCell>
+                                    <TableHeaderCell>"Commission"</TableHeaderCell>
                                     <TableHeaderCell>"Retail Price"</TableHeaderCell>
                                     <TableHeaderCell>"Операция"</TableHeaderCell>
                                     <TableHeaderCell>"Acquiring Fee"</TableHeaderCell>
@@ -118,14 +128,14 @@ pub fn LinksTab(vm: WbSalesDetailsVm) -> impl IntoView {
                                                 >
                                                     <TableCell><TableCellLayout>{rr_dt}</TableCellLayout></TableCell>
                                                     <TableCell><TableCellLayout>{rrd_id}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.ppvz_vw.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.ppvz_vw_nds.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.retail_amount.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.ppvz_for_pay.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.commission_percent.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.retail_price.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_money_opt(report.ppvz_vw)}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_money_opt(report.ppvz_vw_nds)}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_money_opt(report.retail_amount)}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_money_opt(report.ppvz_for_pay)}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_percent_opt(report.commission_percent, 2)}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_money_opt(report.retail_price)}</TableCellLayout></TableCell>
                                                     <TableCell><TableCellLayout>{report.supplier_oper_name.unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.acquiring_fee.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_money_opt(report.acquiring_fee)}</TableCellLayout></TableCell>
                                                 </TableRow>
                                             }
                                         }
@@ -137,5 +147,229 @@ pub fn LinksTab(vm: WbSalesDetailsVm) -> impl IntoView {
                 </CardAnimated>
             }.into_any()
         }}
+        <ManualFinanceLinks vm=manual_vm />
+    }
+}
+
+fn open_finance_report(tabs_store: AppGlobalContext, report: &WbFinanceReportDto) {
+    let tab_key = format!(
+        "p903_wb_finance_report_details_id_{}",
+        urlencoding::encode(&report.id)
+    );
+    let tab_title = format!("WB FR {} #{}", report.rr_dt, report.rrd_id);
+    tabs_store.open_tab(&tab_key, &tab_title);
+}
+
+fn selected_rrd_ids(selected: RwSignal<HashSet<String>>) -> Vec<i64> {
+    selected.with_untracked(|s| s.iter().filter_map(|id| id.parse().ok()).collect())
+}
+
+/// Ручные привязки строк p903 и подбор кандидатов, у которых SRID не совпал
+#[component]
+fn ManualFinanceLinks(vm: WbSalesDetailsVm) -> impl IntoView {
+    let linked_selected = RwSignal::new(HashSet::<String>::new());
+    let candidates_selected = RwSignal::new(HashSet::<String>::new());
+
+    let saving = vm.finance_links_saving;
+    let candidates_loading = vm.finance_link_candidates_loading;
+    let links = vm.finance_links;
+    let candidates = vm.finance_link_candidates;
+    let error = vm.finance_links_error;
+
+    let linked_rows = Signal::derive(move || links.with(|l| l.rows.clone()));
+    let candidate_rows = Signal::derive(move || candidates.get().unwrap_or_default());
+
+    let on_detach = {
+        let vm = vm.clone();
+        move |_| {
+            vm.update_finance_links(selected_rrd_ids(linked_selected), false);
+            linked_selected.set(HashSet::new());
+        }
+    };
+    let on_attach = {
+        let vm = vm.clone();
+        move |_| {
+            vm.update_finance_links(selected_rrd_ids(candidates_selected), true);
+            candidates_selected.set(HashSet::new());
+        }
+    };
+    let on_search = move |_| {
+        candidates_selected.set(HashSet::new());
+        vm.load_finance_link_candidates();
+    };
+
+    view! {
+        <CardAnimated delay_ms=50 nav_id="a012_wb_sales_details_links_manual">
+            <Flex justify=FlexJustify::SpaceBetween align=FlexAlign::Center>
+                <h4 class="details-section__title">"Привязанные вручную"</h4>
+                <Button
+                    appearance=ButtonAppearance::Subtle
+                    size=ButtonSize::Small
+                    on_click=on_detach
+                    disabled=Signal::derive(move || saving.get() || linked_selected.with(|s| s.is_empty()))
+                >
+                    {icon("trash-2")}
+                    {move || format!(" Отвязать выбранные ({})", linked_selected.with(|s| s.len()))}
+                </Button>
+            </Flex>
+
+            {move || error.get().map(|e| view! {
+                <div style="color: var(--color-error); margin-bottom: var(--spacing-sm);">
+                    "Ошибка: " {e}
+                </div>
+            })}
+
+            {move || {
+                let missing = links.with(|l| l.missing_rrd_ids.clone());
+                (!missing.is_empty()).then(|| {
+                    let list = missing.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ");
+                    view! {
+                        <div style="color: var(--color-text-secondary); margin-bottom: var(--spacing-sm);">
+                            {format!("Нет в финансовом отчёте (отчёт не загружен за период): RRD ID {}", list)}
+                        </div>
+                    }
+                })
+            }}
+
+            {move || {
+                if links.with(|l| l.rows.is_empty()) {
+                    view! {
+                        <div style="color: var(--color-text-secondary);">
+                            "Ручных привязок нет. Найдите строки отчёта ниже и привяжите их к продаже."
+                        </div>
+                    }.into_any()
+                } else {
+                    view! { <SelectableFinanceRows rows=linked_rows selected=linked_selected /> }.into_any()
+                }
+            }}
+        </CardAnimated>
+
+        <CardAnimated delay_ms=100 nav_id="a012_wb_sales_details_links_candidates">
+            <Flex justify=FlexJustify::SpaceBetween align=FlexAlign::Center>
+                <h4 class="details-section__title">"Подбор строк отчёта"</h4>
+                <Flex gap=FlexGap::Small>
+                    <Button
+                        appearance=ButtonAppearance::Subtle
+                        size=ButtonSize::Small
+                        on_click=on_search
+                        disabled=Signal::derive(move || candidates_loading.get())
+                    >
+                        {icon("refresh-cw")}
+                        " Найти кандидатов"
+                    </Button>
+                    <Button
+                        appearance=ButtonAppearance::Primary
+                        size=ButtonSize::Small
+                        on_click=on_attach
+                        disabled=Signal::derive(move || saving.get() || candidates_selected.with(|s| s.is_empty()))
+                    >
+                        {icon("plus")}
+                        {move || format!(" Привязать выбранные ({})", candidates_selected.with(|s| s.len()))}
+                    </Button>
+                </Flex>
+            </Flex>
+            <div style="color: var(--color-text-secondary); margin-bottom: var(--spacing-sm);">
+                "Строки с тем же артикулом WB (nm_id) за ±45 дней от даты продажи, SRID которых не совпал с документом."
+            </div>
+
+            {move || {
+                if candidates_loading.get() {
+                    return view! {
+                        <Flex gap=FlexGap::Small style="align-items: center;">
+                            <Spinner size=SpinnerSize::Tiny />
+                            <span>"Поиск..."</span>
+                        </Flex>
+                    }.into_any();
+                }
+                match candidates.with(|c| c.as_ref().map(|rows| rows.is_empty())) {
+                    None => view! { <></> }.into_any(),
+                    Some(true) => view! {
+                        <div style="color: var(--color-text-secondary);">"Подходящих строк не найдено."</div>
+                    }.into_any(),
+                    Some(false) => view! {
+                        <SelectableFinanceRows rows=candidate_rows selected=candidates_selected />
+                    }.into_any(),
+                }
+            }}
+        </CardAnimated>
+    }
+}
+
+/// Таблица строк p903 с чекбоксами выбора (ключ выбора — rrd_id)
+#[component]
+fn SelectableFinanceRows(
+    rows: Signal<Vec<WbFinanceReportDto>>,
+    selected: RwSignal<HashSet<String>>,
+) -> impl IntoView {
+    let tabs_store =
+        leptos::context::use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
+
+    let toggle = Callback::new(move |(id, checked): (String, bool)| {
+        selected.update(|s| {
+            if checked {
+                s.insert(id);
+            } else {
+                s.remove(&id);
+            }
+        });
+    });
+    let toggle_all = Callback::new(move |check_all: bool| {
+        if check_all {
+            selected.set(rows.get_untracked().iter().map(|r| r.rrd_id.to_string()).collect());
+        } else {
+            selected.set(HashSet::new());
+        }
+    });
+
+    view! {
+        <div style="max-height: 360px; overflow: auto;">
+            <Table>
+                <TableHeader>
+                    <TableRow>
+                        <TableHeaderCheckbox
+                            items=rows
+                            selected=selected
+                            get_id=Callback::new(|row: WbFinanceReportDto| row.rrd_id.to_string())
+                            on_change=toggle_all
+                        />
+                        <TableHeaderCell>"Date (rr_dt)"</TableHeaderCell>
+                        <TableHeaderCell>"RRD ID"</TableHeaderCell>
+                        <TableHeaderCell>"SRID"</TableHeaderCell>
+                        <TableHeaderCell>"Операция"</TableHeaderCell>
+                        <TableHeaderCell>"Retail Amount"</TableHeaderCell>
+                        <TableHeaderCell>"PPVZ For Pay"</TableHeaderCell>
+                        <TableHeaderCell>"Acquiring Fee"</TableHeaderCell>
+                    </TableRow>
+                </TableHeader>
+                <TableBody>
+                    <For
+                        each=move || rows.get()
+                        key=|r| r.rrd_id
+                        children=move |report: WbFinanceReportDto| {
+                            let report_for_open = report.clone();
+                            view! {
+                                <TableRow
+                                    on:click=move |_| open_finance_report(tabs_store, &report_for_open)
+                                    attr:style="cursor: pointer;"
+                                >
+                                    <TableCellCheckbox
+                                        item_id=report.rrd_id.to_string()
+                                        selected=selected
+                                        on_change=toggle
+                                    />
+                                    <TableCell><TableCellLayout>{report.rr_dt.clone()}</TableCellLayout></TableCell>
+                                    <TableCell><TableCellLayout>{report.rrd_id}</TableCellLayout></TableCell>
+                                    <TableCell><TableCellLayout>{report.srid.clone().unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
+                                    <TableCell><TableCellLayout>{report.supplier_oper_name.clone().unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
+                                    <TableCell><TableCellLayout>{format_money_opt(report.retail_amount)}</TableCellLayout></TableCell>
+                                    <TableCell><TableCellLayout>{format_money_opt(report.ppvz_for_pay)}</TableCellLayout></TableCell>
+                                    <TableCell><TableCellLayout>{format_money_opt(report.acquiring_fee)}</TableCellLayout></TableCell>
+                                </TableRow>
+                            }
+                        }
+                    />
+                </TableBody>
+            </Table>
+        </div>
     }
 }
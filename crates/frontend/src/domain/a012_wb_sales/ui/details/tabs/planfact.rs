@@ -1,4 +1,5 @@
 use super::super::view_model::WbSalesDetailsVm;
+use crate::shared::money_format::{amount_class, format_money_opt};
 use leptos::prelude::*;
 use thaw::*;
 
@@ -80,11 +81,6 @@ pub fn PlanFactTab(vm: WbSalesDetailsVm) -> impl IntoView {
                 _ => None,
             };
 
-            let fmt = |val: Option<f64>| {
-                val.map(|v| format!("{:.2}", v))
-                    .unwrap_or_else(|| "—".to_string())
-            };
-
             let diff_sell_out = match (sell_out_fact, line.sell_out_plan) {
                 (Some(f), Some(p)) => Some(f - p),
                 _ => None,
@@ -110,62 +106,62 @@ pub fn PlanFactTab(vm: WbSalesDetailsVm) -> impl IntoView {
                 _ => None,
             };
 
-            let rows: Vec<(&str, &str, &str, String, String, String)> = vec![
+            let rows: Vec<(&str, &str, &str, String, String, Option<f64>)> = vec![
                 (
                     "Выручка",
                     "finished_price",
                     "retail_amount (P903)",
-                    fmt(line.sell_out_plan),
-                    fmt(sell_out_fact),
-                    fmt(diff_sell_out),
+                    format_money_opt(line.sell_out_plan),
+                    format_money_opt(sell_out_fact),
+                    diff_sell_out,
                 ),
                 (
                     "Эквайринг",
                     "acquiring_fee_pro * finished_price",
                     "acquiring_fee (P903)",
-                    fmt(line.acquiring_fee_plan),
-                    fmt(acquiring_fee_fact),
-                    fmt(diff_acquiring),
+                    format_money_opt(line.acquiring_fee_plan),
+                    format_money_opt(acquiring_fee_fact),
+                    diff_acquiring,
                 ),
                 (
                     "Прочие комиссии",
                     "0",
                     "rebill_logistic_cost (P903)",
-                    fmt(line.other_fee_plan),
-                    fmt(other_fee_fact),
-                    fmt(diff_other),
+                    format_money_opt(line.other_fee_plan),
+                    format_money_opt(other_fee_fact),
+                    diff_other,
                 ),
                 (
                     "Комиссия",
                     "finished_price - amount_line",
                     "ppvz_vw + ppvz_vw_nds (P903)",
-                    fmt(line.commission_plan),
-                    fmt(commission_fact),
-                    fmt(diff_commission),
+                    format_money_opt(line.commission_plan),
+                    format_money_opt(commission_fact),
+                    diff_commission,
                 ),
                 (
                     "Выплата поставщику",
                     "amount_line - acquiring_fee_plan",
                     "ppvz_for_pay (P903)",
-                    fmt(line.supplier_payout_plan),
-                    fmt(supplier_payout_fact),
-                    fmt(diff_payout),
+                    format_money_opt(line.supplier_payout_plan),
+                    format_money_opt(supplier_payout_fact),
+                    diff_payout,
                 ),
                 (
                     "Себестоимость",
                     "из номенклатуры",
                     "из номенклатуры",
-                    fmt(line.cost_of_production),
-                    fmt(line.cost_of_production),
-                    "—".to_string(),
+                    format_money_opt(line.cost_of_production),
+                    format_money_opt(line.cost_of_production),
+                    None,
                 ),
                 (
                     "Прибыль",
                     "sell_out - acquiring - commission - other - cost",
                     "retail - acquiring - commission - other - cost",
-                    fmt(line.profit_plan),
-                    fmt(profit_fact),
-                    fmt(diff_profit),
+                    format_money_opt(line.profit_plan),
+                    format_money_opt(profit_fact),
+                    diff_profit,
                 ),
             ];
 
@@ -215,7 +211,7 @@ pub fn PlanFactTab(vm: WbSalesDetailsVm) -> impl IntoView {
                                                     <TableCell><TableCellLayout><code>{formula_fact}</code></TableCellLayout></TableCell>
                                                     <TableCell attr:style="text-align: right;"><TableCellLayout>{plan}</TableCellLayout></TableCell>
                                                     <TableCell attr:style="text-align: right;"><TableCellLayout>{fact}</TableCellLayout></TableCell>
-                                                    <TableCell attr:style="text-align: right;"><TableCellLayout>{diff}</TableCellLayout></TableCell>
+                                                    <TableCell attr:style="text-align: right;"><TableCellLayout><span class=diff.map(amount_class).unwrap_or_default()>{format_money_opt(diff)}</span></TableCellLayout></TableCell>
                                                 </TableRow>
                                             }
                                         }
@@ -4,8 +4,12 @@
 
 use super::model::*;
 use crate::layout::global_context::AppGlobalContext;
+use contracts::domain::a012_wb_sales::finance_links::WbSalesFinanceLinksResponse;
+use contracts::domain::common::VersionConflict;
 use contracts::general_ledger::GeneralLedgerEntryDto;
-use contracts::projections::p903_wb_finance_report::dto::WbFinanceReportDto;
+use contracts::projections::p903_wb_finance_report::dto::{
+    WbFinanceReportDto, WbFinanceReportSridTotals,
+};
 use leptos::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
@@ -28,10 +32,20 @@ pub struct WbSalesDetailsVm {
     pub projections_loading: RwSignal<bool>,
 
     pub finance_reports: RwSignal<Vec<WbFinanceReportDto>>,
+    pub finance_reports_totals: RwSignal<WbFinanceReportSridTotals>,
     pub finance_reports_loaded: RwSignal<bool>,
     pub finance_reports_loading: RwSignal<bool>,
     pub finance_reports_error: RwSignal<Option<String>>,
 
+    /// Строки p903, привязанные вручную (SRID не совпал)
+    pub finance_links: RwSignal<WbSalesFinanceLinksResponse>,
+    pub finance_links_loaded: RwSignal<bool>,
+    pub finance_links_error: RwSignal<Option<String>>,
+    pub finance_link_candidates: RwSignal<Option<Vec<WbFinanceReportDto>>>,
+    pub finance_link_candidates_loading: RwSignal<bool>,
+    /// Идёт привязка/отвязка
+    pub finance_links_saving: RwSignal<bool>,
+
     pub general_ledger_entries: RwSignal<Vec<GeneralLedgerEntryDto>>,
     pub general_ledger_entries_loaded: RwSignal<bool>,
     pub general_ledger_entries_loading: RwSignal<bool>,
@@ -48,6 +62,15 @@ pub struct WbSalesDetailsVm {
     pub organization_info: RwSignal<Option<OrganizationInfo>>,
     pub marketplace_info: RwSignal<Option<MarketplaceInfo>>,
 
+    // === Posting preview (confirmation dialog) ===
+    pub post_preview_open: RwSignal<bool>,
+    pub post_preview: RwSignal<Option<serde_json::Value>>,
+    pub post_preview_loading: RwSignal<bool>,
+    pub post_preview_error: RwSignal<Option<String>>,
+
+    /// 409 на проведении/отмене: документ изменён после загрузки
+    pub version_conflict: RwSignal<Option<VersionConflict>>,
+
     // === UI State ===
     pub active_tab: RwSignal<&'static str>,
     pub loading: RwSignal<bool>,
@@ -72,10 +95,18 @@ impl WbSalesDetailsVm {
             projections_loading: RwSignal::new(false),
 
             finance_reports: RwSignal::new(Vec::new()),
+            finance_reports_totals: RwSignal::new(WbFinanceReportSridTotals::default()),
             finance_reports_loaded: RwSignal::new(false),
             finance_reports_loading: RwSignal::new(false),
             finance_reports_error: RwSignal::new(None),
 
+            finance_links: RwSignal::new(WbSalesFinanceLinksResponse::default()),
+            finance_links_loaded: RwSignal::new(false),
+            finance_links_error: RwSignal::new(None),
+            finance_link_candidates: RwSignal::new(None),
+            finance_link_candidates_loading: RwSignal::new(false),
+            finance_links_saving: RwSignal::new(false),
+
             general_ledger_entries: RwSignal::new(Vec::new()),
             general_ledger_entries_loaded: RwSignal::new(false),
             general_ledger_entries_loading: RwSignal::new(false),
@@ -92,6 +123,13 @@ impl WbSalesDetailsVm {
             organization_info: RwSignal::new(None),
             marketplace_info: RwSignal::new(None),
 
+            post_preview_open: RwSignal::new(false),
+            post_preview: RwSignal::new(None),
+            post_preview_loading: RwSignal::new(false),
+            post_preview_error: RwSignal::new(None),
+
+            version_conflict: RwSignal::new(None),
+
             active_tab: RwSignal::new("general"),
             loading: RwSignal::new(false),
             posting: RwSignal::new(false),
@@ -106,6 +144,11 @@ impl WbSalesDetailsVm {
         Signal::derive(move || sale.get().map(|s| s.metadata.is_posted).unwrap_or(false))
     }
 
+    pub fn is_sandbox(&self) -> Signal<bool> {
+        let sale = self.sale;
+        Signal::derive(move || sale.get().map(|s| s.is_sandbox).unwrap_or(false))
+    }
+
     /// Check if document is a customer return
     pub fn is_customer_return(&self) -> Signal<bool> {
         let sale = self.sale;
@@ -162,10 +205,11 @@ impl WbSalesDetailsVm {
         })
     }
 
-    /// Get finance reports count for badge
+    /// Get finance reports count for badge (by SRID + manually linked)
     pub fn finance_reports_count(&self) -> Signal<usize> {
         let reports = self.finance_reports;
-        Signal::derive(move || reports.get().len())
+        let links = self.finance_links;
+        Signal::derive(move || reports.get().len() + links.with(|l| l.rows.len()))
     }
 
     /// Get journal entries count for badge
@@ -204,6 +248,7 @@ impl WbSalesDetailsVm {
                     // Load data for badges immediately (projections, finance reports, journal, attribution)
                     vm.load_projections();
                     vm.load_finance_reports();
+                    vm.load_finance_links();
                     vm.load_general_ledger_entries();
                     vm.load_advert_attribution();
 
@@ -380,9 +425,16 @@ impl WbSalesDetailsVm {
         vm.finance_reports_error.set(None);
 
         spawn_local(async move {
-            match fetch_finance_reports(&srid).await {
-                Ok(reports) => {
+            let result = match fetch_finance_reports(&srid).await {
+                Ok(reports) => fetch_finance_reports_totals(&srid)
+                    .await
+                    .map(|totals| (reports, totals)),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok((reports, totals)) => {
                     vm.finance_reports.set(reports);
+                    vm.finance_reports_totals.set(totals);
                     vm.finance_reports_loaded.set(true);
                 }
                 Err(e) => {
@@ -393,6 +445,82 @@ impl WbSalesDetailsVm {
         });
     }
 
+    /// Load manually linked finance report rows
+    pub fn load_finance_links(&self) {
+        let Some(id) = self.id.get_untracked() else {
+            return;
+        };
+        let vm = self.clone();
+        vm.finance_links_error.set(None);
+        spawn_local(async move {
+            match fetch_finance_links(&id).await {
+                Ok(links) => {
+                    vm.finance_links.set(links);
+                    vm.finance_links_loaded.set(true);
+                }
+                Err(e) => vm.finance_links_error.set(Some(e)),
+            }
+        });
+    }
+
+    /// Search p903 rows that could belong to this sale (same nm_id, near the sale date)
+    pub fn load_finance_link_candidates(&self) {
+        if self.finance_link_candidates_loading.get_untracked() {
+            return;
+        }
+        let Some(id) = self.id.get_untracked() else {
+            return;
+        };
+        let vm = self.clone();
+        vm.finance_link_candidates_loading.set(true);
+        vm.finance_links_error.set(None);
+        spawn_local(async move {
+            match fetch_finance_link_candidates(&id).await {
+                Ok(rows) => vm.finance_link_candidates.set(Some(rows)),
+                Err(e) => vm.finance_links_error.set(Some(e)),
+            }
+            vm.finance_link_candidates_loading.set(false);
+        });
+    }
+
+    /// Attach (`attach = true`) or detach selected p903 rows by rrd_id
+    pub fn update_finance_links(&self, rrd_ids: Vec<i64>, attach: bool) {
+        if rrd_ids.is_empty() || self.finance_links_saving.get_untracked() {
+            return;
+        }
+        let Some(id) = self.id.get_untracked() else {
+            return;
+        };
+        let vm = self.clone();
+        vm.finance_links_saving.set(true);
+        vm.finance_links_error.set(None);
+        spawn_local(async move {
+            let result = if attach {
+                attach_finance_links(&id, rrd_ids.clone()).await
+            } else {
+                detach_finance_links(&id, rrd_ids.clone()).await
+            };
+            match result {
+                Ok(links) => {
+                    vm.finance_links.set(links);
+                    vm.finance_links_loaded.set(true);
+                    // Attached rows are no longer candidates, detached ones may be again
+                    if attach {
+                        vm.finance_link_candidates.update(|c| {
+                            if let Some(rows) = c {
+                                rows.retain(|r| !rrd_ids.contains(&r.rrd_id));
+                            }
+                        });
+                    } else if vm.finance_link_candidates.get_untracked().is_some() {
+                        vm.finance_link_candidates.set(None);
+                    }
+                }
+                Err(e) => vm.finance_links_error.set(Some(e)),
+            }
+            vm.finance_links_saving.set(false);
+        });
+    }
+
     /// Load advert attribution (lazy, for "advert_attribution" tab and badge)
     pub fn load_advert_attribution(&self) {
         if self.advert_attribution_loaded.get_untracked()
@@ -423,24 +551,53 @@ impl WbSalesDetailsVm {
         });
     }
 
+    /// Open the confirmation dialog and load what posting would write to p900/p904
+    pub fn open_post_preview(&self) {
+        let Some(id) = self.id.get() else {
+            return;
+        };
+
+        let vm = self.clone();
+        vm.post_preview.set(None);
+        vm.post_preview_error.set(None);
+        vm.post_preview_loading.set(true);
+        vm.post_preview_open.set(true);
+
+        spawn_local(async move {
+            match fetch_post_preview(&id).await {
+                Ok(data) => vm.post_preview.set(Some(data)),
+                Err(e) => vm.post_preview_error.set(Some(e)),
+            }
+            vm.post_preview_loading.set(false);
+        });
+    }
+
+    /// Confirm posting from the preview dialog
+    pub fn confirm_post(&self) {
+        self.post_preview_open.set(false);
+        self.post();
+    }
+
     /// Post document (проведение)
     pub fn post(&self) {
         let Some(id) = self.id.get() else {
             return;
         };
 
+        let version = self.loaded_version();
         let vm = self.clone();
         vm.posting.set(true);
 
         spawn_local(async move {
-            match post_document(&id).await {
+            match post_document(&id, version).await {
                 Ok(()) => {
                     // Reload document data
                     vm.reload().await;
                 }
-                Err(e) => {
-                    leptos::logging::log!("Error posting: {}", e);
-                }
+                Err(e) => match e.version_conflict() {
+                    Some(conflict) => vm.version_conflict.set(Some(conflict)),
+                    None => leptos::logging::log!("Error posting: {}", e),
+                },
             }
             vm.posting.set(false);
         });
@@ -452,23 +609,37 @@ impl WbSalesDetailsVm {
             return;
         };
 
+        let version = self.loaded_version();
         let vm = self.clone();
         vm.posting.set(true);
 
         spawn_local(async move {
-            match unpost_document(&id).await {
+            match unpost_document(&id, version).await {
                 Ok(()) => {
                     // Reload document data
                     vm.reload().await;
                 }
-                Err(e) => {
-                    leptos::logging::log!("Error unposting: {}", e);
-                }
+                Err(e) => match e.version_conflict() {
+                    Some(conflict) => vm.version_conflict.set(Some(conflict)),
+                    None => leptos::logging::log!("Error unposting: {}", e),
+                },
             }
             vm.posting.set(false);
         });
     }
 
+    /// Version of the document shown on screen (sent with post/unpost)
+    fn loaded_version(&self) -> Option<i32> {
+        self.sale
+            .with_untracked(|s| s.as_ref().map(|s| s.metadata.version))
+    }
+
+    /// Reload the document after a version conflict
+    pub fn reload_document(&self) {
+        let vm = self.clone();
+        spawn_local(async move { vm.reload().await });
+    }
+
     /// Reload document and projections after post/unpost
     async fn reload(&self) {
         let Some(id) = self.id.get() else {
@@ -1,17 +1,36 @@
 pub mod state;
 
-use self::state::{create_state, WbSalesTotals};
+use self::state::{create_state, WbSalesState, WbSalesTotals};
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::change_tokens::ChangeTokenContext;
+use crate::shared::components::calc_columns_dialog::CalcColumnsDialog;
 use crate::shared::components::close_page_button::ClosePageButton;
+use crate::shared::components::data_freshness_badge::DataFreshnessBadges;
 use crate::shared::components::date_range_picker::DateRangePicker;
 use crate::shared::components::pagination_controls::PaginationControls;
+use crate::shared::components::quick_filter_input::QuickFilterInput;
+use crate::shared::components::row_context_menu::{
+    api_url, RawJsonSource, RowAction, RowContextMenu, RowMenuState, RowRef,
+};
 use crate::shared::components::ui::badge::Badge as UiBadge;
 use crate::shared::components::ui::button::Button as UiButton;
+use crate::shared::delta_sync::{
+    delta_url, fetch_delta, merge_rows, spawn_delta_polling, DeltaOutcome, DeltaRow,
+};
 use crate::shared::icons::icon;
 use crate::shared::list_utils::{format_number, get_sort_class, get_sort_indicator, Sortable};
 use crate::shared::page_frame::PageFrame;
 use crate::shared::table_utils::{clear_resize_flag, init_column_resize, was_just_resizing};
+use crate::system::auth::storage;
+use crate::system::operations::ui::{run_documents_operation, track_documents_operation};
+use contracts::domain::a012_wb_sales::aggregate::{WbSalesBatchRequest, WbSalesBatchResponse};
+use contracts::domain::a012_wb_sales::calc_fields::CALC_FIELDS;
+use contracts::domain::a012_wb_sales::compare::MAX_COMPARE_DOCUMENTS;
+use contracts::domain::a012_wb_sales::quick_filter::QUICK_FILTER_FIELDS;
+use contracts::shared::calc_column::{self, CalcColumnDef};
+use contracts::shared::delta::DeltaInfo;
+use contracts::system::data_freshness::DataFreshnessDto;
+use contracts::system::operations::OperationRequest;
 use gloo_net::http::Request;
 use leptos::logging::log;
 use leptos::prelude::*;
@@ -22,9 +41,9 @@ use std::cmp::Ordering;
 use thaw::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
 
 use crate::shared::api_utils::api_base;
+use crate::shared::auth_download::download_authenticated_file;
 use crate::shared::components::table::{
     TableCellCheckbox, TableCellMoney, TableCrosshairHighlight, TableHeaderCheckbox,
 };
@@ -46,6 +65,14 @@ pub struct PaginatedResponse {
     pub total_pages: usize,
     /// Серверные итоги по всему датасету
     pub totals: Option<WbSalesTotals>,
+    /// Водяной знак для дельта-обновления (`updated_after`)
+    #[serde(default)]
+    pub server_time: Option<String>,
+    /// Есть только в ответе на дельта-запрос
+    #[serde(default)]
+    pub delta: Option<DeltaInfo>,
+    #[serde(default)]
+    pub freshness: Vec<DataFreshnessDto>,
 }
 
 /// Форматирует ISO 8601 дату в dd.mm.yyyy
@@ -62,6 +89,12 @@ fn format_date(iso_date: &str) -> String {
 }
 
 /// Parse a single WbSales item from JSON value (compact DTO format)
+impl DeltaRow for WbSalesDto {
+    fn row_id(&self) -> &str {
+        &self.id
+    }
+}
+
 fn parse_wb_sales_item(v: &serde_json::Value, idx: usize) -> Option<WbSalesDto> {
     // All fields are now at top level in compact DTO
     let result = Some(WbSalesDto {
@@ -113,6 +146,11 @@ fn parse_wb_sales_item(v: &serde_json::Value, idx: usize) -> Option<WbSalesDto>
             .and_then(|a| a.as_str())
             .map(|s| s.to_string()),
         prod_cost_resolved_total: v.get("prod_cost_resolved_total").and_then(|a| a.as_f64()),
+        calc_values: v
+            .get("calc_values")
+            .and_then(|a| a.as_array())
+            .map(|values| values.iter().map(|x| x.as_f64()).collect())
+            .unwrap_or_default(),
     });
 
     if result.is_none() {
@@ -148,6 +186,7 @@ pub struct WbSalesDto {
     pub prod_cost_status: Option<String>,
     pub prod_cost_problem_message: Option<String>,
     pub prod_cost_resolved_total: Option<f64>,
+    pub calc_values: Vec<Option<f64>>,
 }
 
 impl Sortable for WbSalesDto {
@@ -252,7 +291,6 @@ pub fn WbSalesList() -> impl IntoView {
 
     // Batch operation state
     let (posting_in_progress, set_posting_in_progress) = signal(false);
-    let (_, set_operation_results) = signal::<Vec<(String, bool, Option<String>)>>(Vec::new());
     let (current_operation, set_current_operation) = signal::<Option<(usize, usize)>>(None);
 
     // Organizations
@@ -261,6 +299,10 @@ pub fn WbSalesList() -> impl IntoView {
     // State for save settings notification
     let (save_notification, set_save_notification) = signal(None::<String>);
 
+    // Диалог вычисляемых колонок
+    let calc_dialog_open = RwSignal::new(false);
+    let calc_columns = Signal::derive(move || state.with(|s| s.calc_columns.clone()));
+
     // Derived signal for selected items count (for button reactivity)
     let selected_count = Signal::derive(move || state.with(|s| s.selected_ids.len()));
 
@@ -271,51 +313,8 @@ pub fn WbSalesList() -> impl IntoView {
             set_loading.set(true);
             set_error.set(None);
 
-            let date_from_val = state.with_untracked(|s| s.date_from.clone());
-            let date_to_val = state.with_untracked(|s| s.date_to.clone());
-            let org_id = state.with_untracked(|s| s.selected_organization_id.clone());
-            let page = state.with_untracked(|s| s.page);
-            let page_size = state.with_untracked(|s| s.page_size);
-            let sort_field = state.with_untracked(|s| s.sort_field.clone());
-            let sort_ascending = state.with_untracked(|s| s.sort_ascending);
-            let search_document_no = state.with_untracked(|s| s.search_document_no.clone());
-            let search_sale_id = state.with_untracked(|s| s.search_sale_id.clone());
-            let search_supplier_article =
-                state.with_untracked(|s| s.search_supplier_article.clone());
-            let offset = page * page_size;
             let cache_buster = js_sys::Date::now() as i64;
-
-            // Build URL with pagination parameters
-            let mut url = format!(
-                "{}/api/a012/wb-sales?date_from={}&date_to={}&limit={}&offset={}&sort_by={}&sort_desc={}&_ts={}",
-                api_base(),
-                date_from_val,
-                date_to_val,
-                page_size,
-                offset,
-                sort_field,
-                !sort_ascending,
-                cache_buster
-            );
-
-            // Add organization filter if selected
-            if let Some(org_id) = org_id {
-                url.push_str(&format!("&organization_id={}", org_id));
-            }
-
-            // Add search filters
-            if !search_document_no.is_empty() {
-                url.push_str(&format!("&search_srid={}", search_document_no));
-            }
-            if !search_sale_id.is_empty() {
-                url.push_str(&format!("&search_sale_id={}", search_sale_id));
-            }
-            if !search_supplier_article.is_empty() {
-                url.push_str(&format!(
-                    "&search_supplier_article={}",
-                    search_supplier_article
-                ));
-            }
+            let url = format!("{}&_ts={}", state.with_untracked(list_url), cache_buster);
 
             log!("Loading WB sales with URL: {}", url);
 
@@ -345,6 +344,8 @@ pub fn WbSalesList() -> impl IntoView {
                                             s.total_count = paginated.total;
                                             s.total_pages = paginated.total_pages;
                                             s.server_totals = paginated.totals;
+                                            s.server_time = paginated.server_time;
+                                            s.freshness = paginated.freshness;
                                             s.is_loaded = true;
                                         });
                                         set_loading.set(false);
@@ -438,6 +439,11 @@ pub fn WbSalesList() -> impl IntoView {
                             {
                                 s.show_total_price = show_tp;
                             }
+                            if let Some(calc) = settings.get("calc_columns").and_then(|v| {
+                                serde_json::from_value::<Vec<CalcColumnDef>>(v.clone()).ok()
+                            }) {
+                                s.calc_columns = calc;
+                            }
                         });
                         log!("Loaded saved settings for A012");
                         load_sales();
@@ -462,20 +468,72 @@ pub fn WbSalesList() -> impl IntoView {
     // dirty-marker + реактивация вкладки: поллер работает в фоне, поэтому список
     // остаётся свежим даже когда вкладка неактивна. prev=None — пропускаем (первичная
     // загрузка идёт из эффекта монтирования).
+    // Дельта-обновление: изменённые строки вливаются в открытую страницу,
+    // полная загрузка — только если изменился состав страницы.
+    let refresh_delta = move || {
+        let since = state.with_untracked(|s| s.server_time.clone().filter(|_| s.is_loaded));
+        let Some(since) = since else {
+            load_sales();
+            return;
+        };
+        let url = delta_url(&state.with_untracked(list_url), &since);
+        spawn_local(async move {
+            match fetch_delta::<PaginatedResponse>(&url).await {
+                Ok(None) => {}
+                Ok(Some(delta)) => {
+                    let info = delta.delta.unwrap_or_default();
+                    let changed: Vec<WbSalesDto> = delta
+                        .items
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(idx, v)| parse_wb_sales_item(v, idx))
+                        .collect();
+                    let mut outcome = DeltaOutcome::Reload;
+                    state.update(|s| {
+                        outcome = merge_rows(
+                            &mut s.sales,
+                            changed,
+                            &info.deleted_ids,
+                            delta.total != s.total_count,
+                            info.truncated,
+                        );
+                        if outcome == DeltaOutcome::Merged {
+                            s.server_totals = delta.totals;
+                            s.server_time = delta.server_time;
+                            s.freshness = delta.freshness;
+                        }
+                    });
+                    if outcome == DeltaOutcome::Reload {
+                        load_sales();
+                    }
+                }
+                Err(e) => log!("WB sales delta refresh failed: {}", e),
+            }
+        });
+    };
+
     let ct = use_context::<ChangeTokenContext>().expect("ChangeTokenContext not found");
     Effect::new(move |prev: Option<u64>| {
         let token = ct.a012_wb_sales.get();
         if prev.is_some() && state.with_untracked(|s| s.is_loaded) {
-            load_sales();
+            refresh_delta();
         }
         token
     });
+    // Страховка для изменений, не отмеченных change token (правки из других
+    // источников): раз в минуту, пока вкладка списка активна.
+    spawn_delta_polling(
+        60_000,
+        move || tabs_store.active.get_untracked().as_deref() == Some("a012_wb_sales"),
+        refresh_delta,
+    );
 
     // Thaw inputs: keep local RwSignal, sync -> state (one-way)
     let search_document_no = RwSignal::new(state.get_untracked().search_document_no.clone());
     let search_sale_id = RwSignal::new(state.get_untracked().search_sale_id.clone());
     let search_supplier_article =
         RwSignal::new(state.get_untracked().search_supplier_article.clone());
+    let quick_filter = RwSignal::new(state.get_untracked().quick_filter.clone());
     let selected_org_id = RwSignal::new(
         state
             .get_untracked()
@@ -531,6 +589,16 @@ pub fn WbSalesList() -> impl IntoView {
         });
     });
 
+    Effect::new(move || {
+        let v = quick_filter.get();
+        untrack(move || {
+            state.update(|s| {
+                s.quick_filter = v;
+                s.page = 0;
+            });
+        });
+    });
+
     Effect::new(move || {
         let v = selected_org_id.get();
         untrack(move || {
@@ -575,6 +643,9 @@ pub fn WbSalesList() -> impl IntoView {
         if !s.search_supplier_article.is_empty() {
             count += 1;
         }
+        if !s.quick_filter.trim().is_empty() {
+            count += 1;
+        }
         count
     });
 
@@ -661,57 +732,15 @@ pub fn WbSalesList() -> impl IntoView {
         }
 
         set_posting_in_progress.set(true);
-        set_operation_results.set(Vec::new());
         set_current_operation.set(Some((0, ids.len())));
 
         spawn_local(async move {
-            let mut results = Vec::new();
-            let ids_vec: Vec<String> = ids.into_iter().collect();
-            let total = ids_vec.len();
-
-            // Разбиваем на чанки по 100
-            for (chunk_idx, chunk) in ids_vec.chunks(100).enumerate() {
-                set_current_operation.set(Some((chunk_idx * 100 + chunk.len(), total)));
-
-                let payload = json!({ "ids": chunk });
-                let response =
-                    Request::post(&format!("{}/api/a012/wb-sales/batch-post", api_base()))
-                        .header("Content-Type", "application/json")
-                        .body(serde_json::to_string(&payload).unwrap_or_default())
-                        .map(|req| req.send());
-
-                match response {
-                    Ok(future) => match future.await {
-                        Ok(resp) => {
-                            if resp.status() == 200 {
-                                for id in chunk {
-                                    results.push((id.clone(), true, None));
-                                }
-                            } else {
-                                for id in chunk {
-                                    results.push((
-                                        id.clone(),
-                                        false,
-                                        Some(format!("HTTP {}", resp.status())),
-                                    ));
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            for id in chunk {
-                                results.push((id.clone(), false, Some(format!("{:?}", e))));
-                            }
-                        }
-                    },
-                    Err(e) => {
-                        for id in chunk {
-                            results.push((id.clone(), false, Some(format!("{:?}", e))));
-                        }
-                    }
-                }
-            }
+            let request = OperationRequest::PostDocuments {
+                entity_type: "a012_wb_sales".to_string(),
+                ids: ids.into_iter().collect(),
+            };
+            run_documents_operation(request, set_current_operation).await;
 
-            set_operation_results.set(results);
             set_posting_in_progress.set(false);
             set_current_operation.set(None);
             state.update(|s| s.selected_ids.clear());
@@ -729,57 +758,15 @@ pub fn WbSalesList() -> impl IntoView {
         }
 
         set_posting_in_progress.set(true);
-        set_operation_results.set(Vec::new());
         set_current_operation.set(Some((0, ids.len())));
 
         spawn_local(async move {
-            let mut results = Vec::new();
-            let ids_vec: Vec<String> = ids.into_iter().collect();
-            let total = ids_vec.len();
-
-            // Разбиваем на чанки по 100
-            for (chunk_idx, chunk) in ids_vec.chunks(100).enumerate() {
-                set_current_operation.set(Some((chunk_idx * 100 + chunk.len(), total)));
-
-                let payload = json!({ "ids": chunk });
-                let response =
-                    Request::post(&format!("{}/api/a012/wb-sales/batch-unpost", api_base()))
-                        .header("Content-Type", "application/json")
-                        .body(serde_json::to_string(&payload).unwrap_or_default())
-                        .map(|req| req.send());
-
-                match response {
-                    Ok(future) => match future.await {
-                        Ok(resp) => {
-                            if resp.status() == 200 {
-                                for id in chunk {
-                                    results.push((id.clone(), true, None));
-                                }
-                            } else {
-                                for id in chunk {
-                                    results.push((
-                                        id.clone(),
-                                        false,
-                                        Some(format!("HTTP {}", resp.status())),
-                                    ));
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            for id in chunk {
-                                results.push((id.clone(), false, Some(format!("{:?}", e))));
-                            }
-                        }
-                    },
-                    Err(e) => {
-                        for id in chunk {
-                            results.push((id.clone(), false, Some(format!("{:?}", e))));
-                        }
-                    }
-                }
-            }
+            let request = OperationRequest::UnpostDocuments {
+                entity_type: "a012_wb_sales".to_string(),
+                ids: ids.into_iter().collect(),
+            };
+            run_documents_operation(request, set_current_operation).await;
 
-            set_operation_results.set(results);
             set_posting_in_progress.set(false);
             set_current_operation.set(None);
             state.update(|s| s.selected_ids.clear());
@@ -789,6 +776,41 @@ pub fn WbSalesList() -> impl IntoView {
         });
     };
 
+    // Проведение всех продаж периода фильтра — пакетом на сервере
+    let post_period = move |_: leptos::ev::MouseEvent| {
+        let date_from = state.with_untracked(|s| s.date_from.clone());
+        let date_to = state.with_untracked(|s| s.date_to.clone());
+        let message = format!(
+            "Провести все продажи WB с датой продажи с {} по {}?",
+            date_from, date_to
+        );
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message(&message).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+
+        set_posting_in_progress.set(true);
+        spawn_local(async move {
+            match start_batch_post_period(date_from, date_to).await {
+                Ok(batch) => {
+                    track_documents_operation(
+                        batch.operation_id,
+                        batch.document_count,
+                        set_current_operation,
+                    )
+                    .await;
+                }
+                Err(e) => set_error.set(Some(format!("Не удалось запустить проведение: {}", e))),
+            }
+
+            set_posting_in_progress.set(false);
+            set_current_operation.set(None);
+            load_sales();
+        });
+    };
+
     // Save current settings to database
     let save_settings_to_db = move |_: leptos::ev::MouseEvent| {
         let settings = json!({
@@ -798,6 +820,7 @@ pub fn WbSalesList() -> impl IntoView {
             "show_operation_date": state.with(|s| s.show_operation_date),
             "show_marketplace_article": state.with(|s| s.show_marketplace_article),
             "show_total_price": state.with(|s| s.show_total_price),
+            "calc_columns": state.with(|s| s.calc_columns.clone()),
         });
 
         spawn_local(async move {
@@ -869,6 +892,11 @@ pub fn WbSalesList() -> impl IntoView {
                         {
                             s.show_total_price = show_tp;
                         }
+                        if let Some(calc) = settings.get("calc_columns").and_then(|v| {
+                            serde_json::from_value::<Vec<CalcColumnDef>>(v.clone()).ok()
+                        }) {
+                            s.calc_columns = calc;
+                        }
                     });
                     suppress_org_reload.set_value(true);
                     selected_org_id.set(restored_org_id);
@@ -907,6 +935,45 @@ pub fn WbSalesList() -> impl IntoView {
         );
     };
 
+    // Сравнение выбранных документов колонками
+    let compare_selected = move |_: leptos::ev::MouseEvent| {
+        let mut ids: Vec<String> = state.with(|s| s.selected_ids.iter().cloned().collect());
+        ids.sort();
+        tabs_store.open_tab(
+            &format!("a012_wb_sales_compare_{}", ids.join(",")),
+            &format!("Сравнение продаж WB ({})", ids.len()),
+        );
+    };
+
+    // Контекстное меню строк (правый клик)
+    let row_menu = RowMenuState::new();
+    let row_actions = vec![
+        RowAction::new("Открыть", "eye", move |row: RowRef| {
+            open_detail(row.id, row.title)
+        }),
+        RowAction::post_to(
+            "Провести",
+            "check",
+            |id| api_url(&format!("/api/a012/wb-sales/{}/post", id)),
+            load_sales,
+        )
+        .write("a012_wb_sales"),
+        RowAction::post_to(
+            "Отменить проведение",
+            "x",
+            |id| api_url(&format!("/api/a012/wb-sales/{}/unpost", id)),
+            load_sales,
+        )
+        .write("a012_wb_sales"),
+        RowAction::copy_id(),
+        RowAction::raw_json(|id| RawJsonSource::ViaDocument {
+            document_url: api_url(&format!("/api/a012/wb-sales/{}", id)),
+            raw_url_prefix: api_url("/api/a012/raw"),
+        })
+        .read("a012_wb_sales"),
+        RowAction::favorite("a012_wb_sales_details", "a012_wb_sales_details_"),
+    ];
+
     view! {
         <PageFrame page_id="a012_wb_sales--list" category="list">
             <div class="page__header">
@@ -915,6 +982,7 @@ pub fn WbSalesList() -> impl IntoView {
                     <UiBadge variant="primary".to_string()>
                         {move || state.get().total_count.to_string()}
                     </UiBadge>
+                    <DataFreshnessBadges items=Signal::derive(move || state.with(|s| s.freshness.clone())) />
                 </div>
 
                 <div class="page__header-right">
@@ -935,43 +1003,59 @@ pub fn WbSalesList() -> impl IntoView {
                             {icon("x")}
                             {move || format!("Unpost ({})", selected_count.get())}
                         </UiButton>
+                        <UiButton
+                            variant="secondary".to_string()
+                            on_click=Callback::new(compare_selected)
+                            disabled=Signal::derive(move || {
+                                let count = selected_count.get();
+                                !(2..=MAX_COMPARE_DOCUMENTS).contains(&count)
+                            })
+                        >
+                            {icon("columns")}
+                            {move || format!("Сравнить ({})", selected_count.get())}
+                        </UiButton>
+                        <UiButton
+                            variant="secondary".to_string()
+                            on_click=Callback::new(post_period)
+                            disabled=Signal::derive(move || posting_in_progress.get())
+                        >
+                            {icon("calendar")}
+                            "Post period"
+                        </UiButton>
+                        <UiButton
+                            variant="secondary".to_string()
+                            on_click=Callback::new(move |_| calc_dialog_open.set(true))
+                        >
+                            {icon("percent")}
+                            {move || match calc_columns.with(|c| c.len()) {
+                                0 => "Вычисляемые".to_string(),
+                                n => format!("Вычисляемые ({})", n),
+                            }}
+                        </UiButton>
                         <UiButton
                             variant="secondary".to_string()
                             on_click=Callback::new(move |_| {
-                                // Отдельная серверная выгрузка: тянем весь отфильтрованный
-                                // датасет (limit = total_count), а не текущую страницу списка.
-                                let date_from = state.with_untracked(|s| s.date_from.clone());
-                                let date_to = state.with_untracked(|s| s.date_to.clone());
-                                let org_id = state.with_untracked(|s| s.selected_organization_id.clone());
-                                let sort_field = state.with_untracked(|s| s.sort_field.clone());
-                                let sort_ascending = state.with_untracked(|s| s.sort_ascending);
-                                let search_document_no = state.with_untracked(|s| s.search_document_no.clone());
-                                let search_sale_id = state.with_untracked(|s| s.search_sale_id.clone());
-                                let search_supplier_article = state.with_untracked(|s| s.search_supplier_article.clone());
-                                let limit = state.with_untracked(|s| s.total_count).max(1);
+                                // Серверная потоковая выгрузка всего отфильтрованного
+                                // датасета, а не текущей страницы списка.
+                                let url = state.with_untracked(|s| {
+                                    export_url(
+                                        &s.date_from,
+                                        &s.date_to,
+                                        s.selected_organization_id.as_deref(),
+                                        &s.sort_field,
+                                        s.sort_ascending,
+                                        &s.search_document_no,
+                                        &s.search_sale_id,
+                                        &s.search_supplier_article,
+                                        &s.quick_filter,
+                                        &s.calc_columns,
+                                    )
+                                });
                                 set_loading.set(true);
                                 spawn_local(async move {
-                                    match fetch_all_sales_for_export(
-                                        date_from,
-                                        date_to,
-                                        org_id,
-                                        sort_field,
-                                        sort_ascending,
-                                        search_document_no,
-                                        search_sale_id,
-                                        search_supplier_article,
-                                        limit,
-                                    ).await {
-                                        Ok(data) => {
-                                            if let Err(e) = export_to_csv(&data) {
-                                                log!("Failed to export: {}", e);
-                                                set_error.set(Some(format!("Ошибка экспорта: {}", e)));
-                                            }
-                                        }
-                                        Err(e) => {
-                                            log!("Failed to fetch sales for export: {}", e);
-                                            set_error.set(Some(format!("Не удалось выгрузить данные: {}", e)));
-                                        }
+                                    if let Err(e) = download_authenticated_file(&url, "wb_sales.csv").await {
+                                        log!("Failed to export sales: {}", e);
+                                        set_error.set(Some(format!("Ошибка экспорта: {}", e)));
                                     }
                                     set_loading.set(false);
                                 });
@@ -1138,6 +1222,17 @@ pub fn WbSalesList() -> impl IntoView {
                                 </Flex>
                             </div>
 
+                            <div style="width: 360px;">
+                                <Flex vertical=true gap=FlexGap::Small>
+                                    <Label>"Быстрый фильтр:"</Label>
+                                    <QuickFilterInput
+                                        value=quick_filter
+                                        fields=QUICK_FILTER_FIELDS
+                                        placeholder="status:unposted warehouse:\"Коледино\" amount>1000"
+                                    />
+                                </Flex>
+                            </div>
+
                             <div style="width: 400px; padding-left: 20px; border-left: 1px solid var(--colorNeutralStroke2);">
                                 <Flex vertical=true gap=FlexGap::Small>
                                     <Label>"Колонки:"</Label>
@@ -1430,6 +1525,11 @@ pub fn WbSalesList() -> impl IntoView {
                                                 </span>
                                             </div>
                                         </TableHeaderCell>
+                                        {move || calc_columns.get().into_iter().map(|def| view! {
+                                            <TableHeaderCell resizable=false min_width=90.0 attr:title=def.expr.clone()>
+                                                <div style="text-align: right; font-style: italic;">{def.name}</div>
+                                            </TableHeaderCell>
+                                        }).collect_view()}
                                         </TableRow>
                                     </TableHeader>
 
@@ -1464,12 +1564,14 @@ pub fn WbSalesList() -> impl IntoView {
                                                 };
                                                 let total = item.total_price;
                                                 let finished = item.finished_price;
+                                                let calc_values = item.calc_values.clone();
 
                                                 let id_for_open = id.clone();
                                                 let document_no_for_title = document_no.clone();
+                                                let row_ref = RowRef::new(id.clone(), document_no.clone());
 
                                                 view! {
-                                                <TableRow>
+                                                <TableRow on:contextmenu=move |ev| row_menu.open_at(&ev, row_ref.clone())>
                                                     <TableCellCheckbox
                                                         item_id=id.clone()
                                                         selected=selected_signal
@@ -1518,6 +1620,9 @@ pub fn WbSalesList() -> impl IntoView {
                                                         <TableCellMoney value=total show_currency=false color_by_sign=false />
                                                     </TableCell>
                                                     <TableCellMoney value=finished show_currency=false color_by_sign=false />
+                                                    {calc_values.into_iter().map(|value| view! {
+                                                        <TableCellMoney value=value show_currency=false color_by_sign=true />
+                                                    }).collect_view()}
                                                 </TableRow>
                                             }
                                         }).collect_view()}
@@ -1525,6 +1630,16 @@ pub fn WbSalesList() -> impl IntoView {
                                 </Table>
             </div>
             </div>
+            <RowContextMenu state=row_menu actions=row_actions />
+            <CalcColumnsDialog
+                open=calc_dialog_open
+                columns=calc_columns
+                fields=CALC_FIELDS
+                on_apply=Callback::new(move |defs: Vec<CalcColumnDef>| {
+                    state.update(|s| s.calc_columns = defs);
+                    load_sales();
+                })
+            />
         </PageFrame>
     }
 }
@@ -1648,177 +1763,128 @@ async fn fetch_organizations() -> Result<Vec<Organization>, String> {
     Ok(data)
 }
 
-/// Отдельная серверная выгрузка для CSV: тянет весь отфильтрованный датасет одним
-/// запросом (offset=0, limit=total), независимо от размера страницы в списке.
-/// Список ограничен 500 строками на странице; полная выгрузка живёт отдельно здесь.
-#[allow(clippy::too_many_arguments)]
-async fn fetch_all_sales_for_export(
+/// Ставит в очередь проведение всех продаж периода (по дате продажи).
+async fn start_batch_post_period(
     date_from: String,
     date_to: String,
-    org_id: Option<String>,
-    sort_field: String,
+) -> Result<WbSalesBatchResponse, String> {
+    let token = storage::get_access_token().ok_or_else(|| "Not authenticated".to_string())?;
+    let request = WbSalesBatchRequest {
+        ids: Vec::new(),
+        date_from: Some(date_from),
+        date_to: Some(date_to),
+    };
+    let response = Request::post(&format!("{}/api/a012/wb-sales/batch-post", api_base()))
+        .header("Authorization", &format!("Bearer {}", token))
+        .json(&request)
+        .map_err(|e| format!("{e:?}"))?
+        .send()
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+    if !response.ok() {
+        let text = response.text().await.unwrap_or_default();
+        return Err(if text.is_empty() {
+            format!("HTTP {}", response.status())
+        } else {
+            text
+        });
+    }
+    response.json().await.map_err(|e| format!("{e}"))
+}
+
+/// URL серверной CSV-выгрузки по текущим фильтрам списка.
+#[allow(clippy::too_many_arguments)]
+/// URL списка по текущим фильтрам, сортировке и странице.
+fn list_url(s: &WbSalesState) -> String {
+    let mut url = format!(
+        "{}/api/a012/wb-sales?date_from={}&date_to={}&limit={}&offset={}&sort_by={}&sort_desc={}",
+        api_base(),
+        s.date_from,
+        s.date_to,
+        s.page_size,
+        s.page * s.page_size,
+        s.sort_field,
+        !s.sort_ascending
+    );
+
+    // Add organization filter if selected
+    if let Some(org_id) = &s.selected_organization_id {
+        url.push_str(&format!("&organization_id={}", org_id));
+    }
+
+    // Add search filters
+    if !s.search_document_no.is_empty() {
+        url.push_str(&format!("&search_srid={}", s.search_document_no));
+    }
+    if !s.search_sale_id.is_empty() {
+        url.push_str(&format!("&search_sale_id={}", s.search_sale_id));
+    }
+    if !s.search_supplier_article.is_empty() {
+        url.push_str(&format!(
+            "&search_supplier_article={}",
+            s.search_supplier_article
+        ));
+    }
+    if !s.quick_filter.trim().is_empty() {
+        url.push_str(&format!("&q={}", urlencoding::encode(&s.quick_filter)));
+    }
+    let calc = calc_column::to_param(&s.calc_columns);
+    if !calc.is_empty() {
+        url.push_str(&format!("&calc={}", urlencoding::encode(&calc)));
+    }
+    url
+}
+
+fn export_url(
+    date_from: &str,
+    date_to: &str,
+    org_id: Option<&str>,
+    sort_field: &str,
     sort_ascending: bool,
-    search_document_no: String,
-    search_sale_id: String,
-    search_supplier_article: String,
-    limit: usize,
-) -> Result<Vec<WbSalesDto>, String> {
-    let cache_buster = js_sys::Date::now() as i64;
+    search_document_no: &str,
+    search_sale_id: &str,
+    search_supplier_article: &str,
+    quick_filter: &str,
+    calc_columns: &[CalcColumnDef],
+) -> String {
     let mut url = format!(
-        "{}/api/a012/wb-sales?date_from={}&date_to={}&limit={}&offset=0&sort_by={}&sort_desc={}&_ts={}",
+        "{}/api/a012/wb-sales/export?format=csv&date_from={}&date_to={}&sort_by={}&sort_desc={}",
         api_base(),
         date_from,
         date_to,
-        limit,
         sort_field,
-        !sort_ascending,
-        cache_buster
+        !sort_ascending
     );
 
-    if let Some(org_id) = org_id {
-        if !org_id.is_empty() {
-            url.push_str(&format!("&organization_id={}", org_id));
-        }
+    if let Some(org_id) = org_id.filter(|id| !id.is_empty()) {
+        url.push_str(&format!("&organization_id={}", org_id));
     }
     if !search_document_no.is_empty() {
-        url.push_str(&format!("&search_srid={}", search_document_no));
+        url.push_str(&format!(
+            "&search_srid={}",
+            urlencoding::encode(search_document_no)
+        ));
     }
     if !search_sale_id.is_empty() {
-        url.push_str(&format!("&search_sale_id={}", search_sale_id));
+        url.push_str(&format!(
+            "&search_sale_id={}",
+            urlencoding::encode(search_sale_id)
+        ));
     }
     if !search_supplier_article.is_empty() {
         url.push_str(&format!(
             "&search_supplier_article={}",
-            search_supplier_article
+            urlencoding::encode(search_supplier_article)
         ));
     }
-
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("{e:?}"))?;
-    if response.status() != 200 {
-        return Err(format!("HTTP {}", response.status()));
+    if !quick_filter.trim().is_empty() {
+        url.push_str(&format!("&q={}", urlencoding::encode(quick_filter)));
     }
-    let text = response.text().await.map_err(|e| format!("{e:?}"))?;
-    let paginated: PaginatedResponse = serde_json::from_str(&text).map_err(|e| format!("{e}"))?;
-    let items: Vec<WbSalesDto> = paginated
-        .items
-        .into_iter()
-        .enumerate()
-        .filter_map(|(idx, v)| parse_wb_sales_item(&v, idx))
-        .collect();
-    Ok(items)
-}
-
-/// Экспорт WB Sales в CSV для Excel
-fn export_to_csv(data: &[WbSalesDto]) -> Result<(), String> {
-    // UTF-8 BOM для правильного отображения кириллицы в Excel
-    let mut csv = String::from("\u{FEFF}");
-
-    // Заголовок с точкой с запятой как разделитель
-    csv.push_str("Document №;Дата продажи;Дата операции;Организация;Артикул;Артикул МП;Артикул 1С;Код 1С;Название;Количество;К выплате;Дил. цена УТ;Полная цена;Итоговая цена\n");
-
-    for sale in data {
-        let sale_date = format_date(&sale.sale_date);
-        let operation_date = sale
-            .operation_date
-            .as_ref()
-            .map(|s| s.as_str())
-            .unwrap_or("—");
-        let org_name = sale
-            .organization_name
-            .as_ref()
-            .map(|s| s.as_str())
-            .unwrap_or("—");
-        let mp_article = sale
-            .marketplace_article
-            .as_ref()
-            .map(|s| s.as_str())
-            .unwrap_or("—");
-        let nom_code = sale
-            .nomenclature_code
-            .as_ref()
-            .map(|s| s.as_str())
-            .unwrap_or("—");
-        let nom_article = sale
-            .nomenclature_article
-            .as_ref()
-            .map(|s| s.as_str())
-            .unwrap_or("—");
-
-        // Форматируем суммы с запятой как десятичный разделитель
-        let qty_str = format!("{:.0}", sale.qty);
-        let amount_str = sale
-            .amount_line
-            .map(|a| format!("{:.2}", a).replace(".", ","))
-            .unwrap_or_else(|| "—".to_string());
-        let dealer_price_str = sale
-            .dealer_price_ut
-            .map(|a| format!("{:.2}", a).replace(".", ","))
-            .unwrap_or_else(|| "—".to_string());
-        let total_price_str = sale
-            .total_price
-            .map(|a| format!("{:.2}", a).replace(".", ","))
-            .unwrap_or_else(|| "—".to_string());
-        let finished_price_str = sale
-            .finished_price
-            .map(|a| format!("{:.2}", a).replace(".", ","))
-            .unwrap_or_else(|| "—".to_string());
-
-        csv.push_str(&format!(
-            "\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";{};{};{};{};{}\n",
-            sale.document_no.replace('\"', "\"\""),
-            sale_date,
-            operation_date.replace('\"', "\"\""),
-            org_name.replace('\"', "\"\""),
-            sale.supplier_article.replace('\"', "\"\""),
-            mp_article.replace('\"', "\"\""),
-            nom_article.replace('\"', "\"\""),
-            nom_code.replace('\"', "\"\""),
-            sale.name.replace('\"', "\"\""),
-            qty_str,
-            amount_str,
-            dealer_price_str,
-            total_price_str,
-            finished_price_str
+    if !calc_columns.is_empty() {
+        url.push_str(&format!(
+            "&calc={}",
+            urlencoding::encode(&calc_column::to_param(calc_columns))
         ));
     }
-
-    // Создаем Blob с CSV данными
-    let blob_parts = js_sys::Array::new();
-    blob_parts.push(&wasm_bindgen::JsValue::from_str(&csv));
-
-    let blob_props = BlobPropertyBag::new();
-    blob_props.set_type("text/csv;charset=utf-8;");
-
-    let blob = Blob::new_with_str_sequence_and_options(&blob_parts, &blob_props)
-        .map_err(|e| format!("Failed to create blob: {:?}", e))?;
-
-    // Создаем URL для blob
-    let url = Url::create_object_url_with_blob(&blob)
-        .map_err(|e| format!("Failed to create URL: {:?}", e))?;
-
-    // Создаем временную ссылку для скачивания
-    let window = web_sys::window().ok_or_else(|| "no window".to_string())?;
-    let document = window.document().ok_or_else(|| "no document".to_string())?;
-
-    let a = document
-        .create_element("a")
-        .map_err(|e| format!("Failed to create element: {:?}", e))?
-        .dyn_into::<HtmlAnchorElement>()
-        .map_err(|e| format!("Failed to cast to anchor: {:?}", e))?;
-
-    a.set_href(&url);
-    let filename = format!(
-        "wb_sales_{}.csv",
-        chrono::Utc::now().format("%Y%m%d_%H%M%S")
-    );
-    a.set_download(&filename);
-    a.click();
-
-    // Освобождаем URL
-    Url::revoke_object_url(&url).map_err(|e| format!("Failed to revoke URL: {:?}", e))?;
-
-    Ok(())
+    url
 }
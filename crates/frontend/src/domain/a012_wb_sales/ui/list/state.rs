@@ -1,5 +1,7 @@
 use super::WbSalesDto;
 use chrono::{Datelike, Utc};
+use contracts::shared::calc_column::CalcColumnDef;
+use contracts::system::data_freshness::DataFreshnessDto;
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -23,12 +25,20 @@ pub struct WbSalesState {
     pub search_document_no: String,
     pub search_sale_id: String,
     pub search_supplier_article: String,
+    /// Быстрый фильтр: `status:unposted warehouse:"Коледино" amount>1000`
+    pub quick_filter: String,
     // Серверные итоги
     pub server_totals: Option<WbSalesTotals>,
+    /// `server_time` последнего ответа — с него запрашивается дельта
+    pub server_time: Option<String>,
+    /// Свежесть данных WB продаж (бейдж «данные на …»)
+    pub freshness: Vec<DataFreshnessDto>,
     // Column visibility settings
     pub show_operation_date: bool,
     pub show_marketplace_article: bool,
     pub show_total_price: bool,
+    /// Вычисляемые колонки (считаются на сервере, `?calc=`)
+    pub calc_columns: Vec<CalcColumnDef>,
 }
 
 /// Серверные итоги по датасету WB Sales
@@ -76,12 +86,16 @@ impl Default for WbSalesState {
             search_document_no: String::new(),
             search_sale_id: String::new(),
             search_supplier_article: String::new(),
+            quick_filter: String::new(),
             // Итоги
             server_totals: None,
+            server_time: None,
+            freshness: Vec::new(),
             // Column visibility defaults (hidden by default)
             show_operation_date: false,
             show_marketplace_article: false,
             show_total_price: false,
+            calc_columns: Vec::new(),
         }
     }
 }
@@ -27,6 +27,8 @@ pub struct YmOrderDetailDto {
     pub source_meta: SourceMetaDto,
     pub metadata: MetadataDto,
     #[serde(default)]
+    pub is_sandbox: bool,
+    #[serde(default)]
     pub is_error: bool,
 }
 
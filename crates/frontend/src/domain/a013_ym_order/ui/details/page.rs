@@ -5,8 +5,11 @@ use super::tabs::{
 };
 use super::view_model::YmOrderDetailsVm;
 use crate::layout::global_context::AppGlobalContext;
+use crate::shared::components::sandbox_badge::SandboxBadge;
 use crate::shared::icons::icon;
 use crate::shared::page_frame::PageFrame;
+use crate::system::document_audit::ui::DocumentHistoryPanel;
+use crate::system::scheduled_posts::ui::ScheduledPostControl;
 use leptos::prelude::*;
 use thaw::*;
 
@@ -92,6 +95,7 @@ pub fn YmOrderDetail(id: String, #[prop(into)] on_close: Callback<()>) -> impl I
 #[component]
 fn Header(vm: YmOrderDetailsVm, on_close: Callback<()>) -> impl IntoView {
     let is_posted = vm.is_posted();
+    let is_sandbox = vm.is_sandbox();
     let document_no = vm.document_no();
     let order = vm.order;
 
@@ -112,9 +116,11 @@ fn Header(vm: YmOrderDetailsVm, on_close: Callback<()>) -> impl IntoView {
                         }
                     }}
                 </Show>
+                <SandboxBadge is_sandbox=is_sandbox />
             </div>
             <div class="modal-header-actions">
                 <OrderHistoryButton vm=vm.clone() />
+                <ScheduledPostControl entity_type="a013_ym_order" document_id=vm.id />
                 <PostButtons vm=vm.clone() />
                 <Button
                     appearance=ButtonAppearance::Secondary
@@ -281,7 +287,10 @@ fn TabBar(vm: YmOrderDetailsVm) -> impl IntoView {
             <button
                 class="page__tab"
                 class:page__tab--active=move || active_tab.get() == "projections"
-                on:click=move |_| vm.set_tab("projections")
+                on:click={
+                    let vm = vm.clone();
+                    move |_| vm.set_tab("projections")
+                }
             >
                 {icon("layers")} "Проекции"
                 <Badge
@@ -299,6 +308,14 @@ fn TabBar(vm: YmOrderDetailsVm) -> impl IntoView {
                     {move || projections_count.get().to_string()}
                 </Badge>
             </button>
+
+            <button
+                class="page__tab"
+                class:page__tab--active=move || active_tab.get() == "history"
+                on:click=move |_| vm.set_tab("history")
+            >
+                {icon("clock")} "История"
+            </button>
         </div>
     }
 }
@@ -315,6 +332,7 @@ fn TabContent(vm: YmOrderDetailsVm) -> impl IntoView {
     let vm_links = vm.clone();
     let vm_json = vm.clone();
     let vm_projections = vm.clone();
+    let document_id = vm.id;
 
     view! {
         {move || match active_tab.get() {
@@ -325,6 +343,7 @@ fn TabContent(vm: YmOrderDetailsVm) -> impl IntoView {
             "links"       => view! { <LinksTab       vm=vm_links.clone()       /> }.into_any(),
             "json"        => view! { <JsonTab        vm=vm_json.clone()        /> }.into_any(),
             "projections" => view! { <ProjectionsTab vm=vm_projections.clone() /> }.into_any(),
+            "history"     => view! { <DocumentHistoryPanel entity_type="a013_ym_order" document_id=document_id /> }.into_any(),
             _             => view! { <GeneralTab     vm=vm_general.clone()     /> }.into_any(),
         }}
     }
@@ -78,6 +78,11 @@ impl YmOrderDetailsVm {
         Signal::derive(move || order.get().map(|o| o.metadata.is_posted).unwrap_or(false))
     }
 
+    pub fn is_sandbox(&self) -> Signal<bool> {
+        let order = self.order;
+        Signal::derive(move || order.get().map(|o| o.is_sandbox).unwrap_or(false))
+    }
+
     pub fn document_no(&self) -> Signal<String> {
         let order = self.order;
         Signal::derive(move || {
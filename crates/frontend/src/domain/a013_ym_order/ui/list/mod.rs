@@ -13,7 +13,10 @@ use crate::shared::icons::icon;
 use crate::shared::list_utils::{get_sort_class, get_sort_indicator, Sortable};
 use crate::shared::page_frame::PageFrame;
 use crate::shared::table_utils::init_column_resize;
+use crate::system::auth::context::{can_view_finance, use_auth};
+use crate::system::operations::api::run_operation;
 use contracts::domain::a013_ym_order::aggregate::YmOrderListDto;
+use contracts::system::operations::OperationRequest;
 use gloo_net::http::Request;
 use leptos::logging::log;
 use leptos::prelude::*;
@@ -134,6 +137,9 @@ pub fn YmOrderList() -> impl IntoView {
 
     // CSV export (full dataset, not just current page)
     let (exporting, set_exporting) = signal(false);
+    // Суммы скрыты для ролей без доступа к финансам (backend отдаёт их обнулёнными).
+    let (auth_state, _) = use_auth();
+    let show_finance = can_view_finance(auth_state);
 
     // Organizations
     let (organizations, set_organizations) = signal::<Vec<Organization>>(Vec::new());
@@ -572,7 +578,7 @@ pub fn YmOrderList() -> impl IntoView {
                                     .await
                                     {
                                         Ok(data) => {
-                                            if let Err(e) = export_to_csv(&data) {
+                                            if let Err(e) = export_to_csv(&data, show_finance) {
                                                 log!("Failed to export: {}", e);
                                             }
                                         }
@@ -673,13 +679,11 @@ pub fn YmOrderList() -> impl IntoView {
                                         }
                                         set_posting_in_progress.set(true);
                                         spawn_local(async move {
-                                            let payload = json!({ "ids": ids });
-                                            match Request::post(&format!("{}/api/a013/ym-order/batch-post", api_base()))
-                                                .header("Content-Type", "application/json")
-                                                .body(serde_json::to_string(&payload).unwrap_or_default())
-                                                .unwrap()
-                                                .send()
-                                                .await
+                                            let request = OperationRequest::PostDocuments {
+                                                entity_type: "a013_ym_order".to_string(),
+                                                ids,
+                                            };
+                                            match run_operation(request, |_, _| {}).await
                                             {
                                                 Ok(_) => {
                                                     state.update(|s| s.selected_ids.clear());
@@ -687,7 +691,7 @@ pub fn YmOrderList() -> impl IntoView {
                                                     load_orders();
                                                 }
                                                 Err(e) => {
-                                                    log!("Failed to post: {:?}", e);
+                                                    log!("Failed to post: {}", e);
                                                 }
                                             }
                                             set_posting_in_progress.set(false);
@@ -706,13 +710,11 @@ pub fn YmOrderList() -> impl IntoView {
                                         }
                                         set_posting_in_progress.set(true);
                                         spawn_local(async move {
-                                            let payload = json!({ "ids": ids });
-                                            match Request::post(&format!("{}/api/a013/ym-order/batch-unpost", api_base()))
-                                                .header("Content-Type", "application/json")
-                                                .body(serde_json::to_string(&payload).unwrap_or_default())
-                                                .unwrap()
-                                                .send()
-                                                .await
+                                            let request = OperationRequest::UnpostDocuments {
+                                                entity_type: "a013_ym_order".to_string(),
+                                                ids,
+                                            };
+                                            match run_operation(request, |_, _| {}).await
                                             {
                                                 Ok(_) => {
                                                     state.update(|s| s.selected_ids.clear());
@@ -720,7 +722,7 @@ pub fn YmOrderList() -> impl IntoView {
                                                     load_orders();
                                                 }
                                                 Err(e) => {
-                                                    log!("Failed to unpost: {:?}", e);
+                                                    log!("Failed to unpost: {}", e);
                                                 }
                                             }
                                             set_posting_in_progress.set(false);
@@ -908,50 +910,52 @@ pub fn YmOrderList() -> impl IntoView {
                                     </div>
                                 </TableHeaderCell>
 
-                                <TableHeaderCell resizable=false min_width=100.0 class="resizable">
-                                    <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("total_amount")>
-                                        "Сумма"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "total_amount"))>
-                                            {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "total_amount", state.with(|s| s.sort_ascending))}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-
-                                <TableHeaderCell resizable=false min_width=100.0 class="resizable">
-                                    <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("delivery_total")>
-                                        "Доставка"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "delivery_total"))>
-                                            {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "delivery_total", state.with(|s| s.sort_ascending))}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-
-                                <TableHeaderCell resizable=false min_width=100.0 class="resizable">
-                                    <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("subsidies_total")>
-                                        "Субсидии"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "subsidies_total"))>
-                                            {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "subsidies_total", state.with(|s| s.sort_ascending))}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-
-                                <TableHeaderCell resizable=false min_width=110.0 class="resizable">
-                                    <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("total_dealer_amount")>
-                                        "Дилер. сумма УТ"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "total_dealer_amount"))>
-                                            {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "total_dealer_amount", state.with(|s| s.sort_ascending))}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-
-                                <TableHeaderCell resizable=false min_width=90.0 class="resizable">
-                                    <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("margin_pro")>
-                                        "Маржа, %"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "margin_pro"))>
-                                            {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "margin_pro", state.with(|s| s.sort_ascending))}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
+                                {show_finance.then(|| view! {
+                                    <TableHeaderCell resizable=false min_width=100.0 class="resizable">
+                                        <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("total_amount")>
+                                            "Сумма"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "total_amount"))>
+                                                {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "total_amount", state.with(|s| s.sort_ascending))}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+
+                                    <TableHeaderCell resizable=false min_width=100.0 class="resizable">
+                                        <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("delivery_total")>
+                                            "Доставка"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "delivery_total"))>
+                                                {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "delivery_total", state.with(|s| s.sort_ascending))}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+
+                                    <TableHeaderCell resizable=false min_width=100.0 class="resizable">
+                                        <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("subsidies_total")>
+                                            "Субсидии"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "subsidies_total"))>
+                                                {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "subsidies_total", state.with(|s| s.sort_ascending))}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+
+                                    <TableHeaderCell resizable=false min_width=110.0 class="resizable">
+                                        <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("total_dealer_amount")>
+                                            "Дилер. сумма УТ"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "total_dealer_amount"))>
+                                                {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "total_dealer_amount", state.with(|s| s.sort_ascending))}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+
+                                    <TableHeaderCell resizable=false min_width=90.0 class="resizable">
+                                        <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("margin_pro")>
+                                            "Маржа, %"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "margin_pro"))>
+                                                {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "margin_pro", state.with(|s| s.sort_ascending))}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+                                })}
                             </TableRow>
                         </TableHeader>
 
@@ -1046,35 +1050,37 @@ pub fn YmOrderList() -> impl IntoView {
                                                 </TableCellLayout>
                                             </TableCell>
 
-                                            <TableCellMoney
-                                                value=order.total_amount
-                                                show_currency=false
-                                                color_by_sign=false
-                                            />
-
-                                            <TableCellMoney
-                                                value=order.delivery_total.unwrap_or(0.0)
-                                                show_currency=false
-                                                color_by_sign=false
-                                            />
-
-                                            <TableCellMoney
-                                                value=order.subsidies_total
-                                                show_currency=false
-                                                color_by_sign=false
-                                            />
-
-                                            <TableCellMoney
-                                                value=order.total_dealer_amount.unwrap_or(0.0)
-                                                show_currency=false
-                                                color_by_sign=false
-                                            />
-
-                                            <TableCell>
-                                                <TableCellLayout>
-                                                    {order.margin_pro.map(|v| format!("{:.1}%", v)).unwrap_or_else(|| "—".to_string())}
-                                                </TableCellLayout>
-                                            </TableCell>
+                                            {show_finance.then(|| view! {
+                                                <TableCellMoney
+                                                    value=order.total_amount
+                                                    show_currency=false
+                                                    color_by_sign=false
+                                                />
+
+                                                <TableCellMoney
+                                                    value=order.delivery_total.unwrap_or(0.0)
+                                                    show_currency=false
+                                                    color_by_sign=false
+                                                />
+
+                                                <TableCellMoney
+                                                    value=order.subsidies_total
+                                                    show_currency=false
+                                                    color_by_sign=false
+                                                />
+
+                                                <TableCellMoney
+                                                    value=order.total_dealer_amount.unwrap_or(0.0)
+                                                    show_currency=false
+                                                    color_by_sign=false
+                                                />
+
+                                                <TableCell>
+                                                    <TableCellLayout>
+                                                        {order.margin_pro.map(|v| format!("{:.1}%", v)).unwrap_or_else(|| "—".to_string())}
+                                                    </TableCellLayout>
+                                                </TableCell>
+                                            })}
                                         </TableRow>
                                     }
                             }).collect_view()}
@@ -1228,7 +1234,8 @@ async fn fetch_all_orders_for_export(
 }
 
 /// Экспорт заказов YM в CSV для Excel.
-fn export_to_csv(data: &[YmOrderListDto]) -> Result<(), String> {
+/// Без доступа к финансам колонки сумм в файл не попадают.
+fn export_to_csv(data: &[YmOrderListDto], show_finance: bool) -> Result<(), String> {
     use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
 
     let money = |v: f64| format!("{:.2}", v).replace('.', ",");
@@ -1238,7 +1245,11 @@ fn export_to_csv(data: &[YmOrderListDto]) -> Result<(), String> {
     };
 
     let mut csv = String::from("\u{FEFF}");
-    csv.push_str("Order №;Кампания;Дата заказа;Дата доставки;Дата реализации;Дата оплаты;Организация;Статус;Ошибка;Кол-во;Сумма;Доставка;Субсидии;Дилер. сумма УТ;Маржа, %;Проведён\n");
+    csv.push_str("Order №;Кампания;Дата заказа;Дата доставки;Дата реализации;Дата оплаты;Организация;Статус;Ошибка;Кол-во;");
+    if show_finance {
+        csv.push_str("Сумма;Доставка;Субсидии;Дилер. сумма УТ;Маржа, %;");
+    }
+    csv.push_str("Проведён\n");
 
     for o in data {
         let realization = if o.realization_date.is_empty() {
@@ -1252,13 +1263,25 @@ fn export_to_csv(data: &[YmOrderListDto]) -> Result<(), String> {
             format_date(&o.payment_date)
         };
         let org_name = o.organization_name.as_deref().unwrap_or("");
-        let margin_str = o
-            .margin_pro
-            .map(|v| format!("{:.1}", v).replace('.', ","))
-            .unwrap_or_default();
+        let finance = if show_finance {
+            let margin_str = o
+                .margin_pro
+                .map(|v| format!("{:.1}", v).replace('.', ","))
+                .unwrap_or_default();
+            format!(
+                "{};{};{};{};{};",
+                money(o.total_amount),
+                opt_money(o.delivery_total),
+                money(o.subsidies_total),
+                opt_money(o.total_dealer_amount),
+                margin_str,
+            )
+        } else {
+            String::new()
+        };
 
         csv.push_str(&format!(
-            "\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";{};{};{};{};{};{};\"{}\"\n",
+            "\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";{};{}\"{}\"\n",
             o.document_no.replace('\"', "\"\""),
             o.campaign_id.replace('\"', "\"\""),
             format_date(&o.creation_date),
@@ -1269,11 +1292,7 @@ fn export_to_csv(data: &[YmOrderListDto]) -> Result<(), String> {
             o.status_norm.replace('\"', "\"\""),
             if o.is_error { "Да" } else { "Нет" },
             format!("{:.0}", o.total_qty),
-            money(o.total_amount),
-            opt_money(o.delivery_total),
-            money(o.subsidies_total),
-            opt_money(o.total_dealer_amount),
-            margin_str,
+            finance,
             if o.is_posted { "Да" } else { "Нет" },
         ));
     }
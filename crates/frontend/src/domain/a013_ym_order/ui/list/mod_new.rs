@@ -5,7 +5,9 @@ use crate::shared::components::month_selector::MonthSelector;
 use crate::shared::list_utils::{
     format_number, format_number_int, get_sort_class, get_sort_indicator, Sortable,
 };
+use crate::system::operations::ui::run_documents_operation;
 use contracts::domain::a013_ym_order::aggregate::YmOrderListDto;
+use contracts::system::operations::OperationRequest;
 use gloo_net::http::Request;
 use leptos::logging::log;
 use leptos::prelude::*;
@@ -100,7 +102,6 @@ pub fn YmOrderList() -> impl IntoView {
 
     // Batch operation state
     let (posting_in_progress, set_posting_in_progress) = signal(false);
-    let (_, set_operation_results) = signal::<Vec<(String, bool, Option<String>)>>(Vec::new());
     let (current_operation, set_current_operation) = signal::<Option<(usize, usize)>>(None);
 
     // Organizations
@@ -351,55 +352,15 @@ pub fn YmOrderList() -> impl IntoView {
         }
 
         set_posting_in_progress.set(true);
-        set_operation_results.set(Vec::new());
         set_current_operation.set(Some((0, ids.len())));
 
         spawn_local(async move {
-            let mut results = Vec::new();
-            let total = ids.len();
-
-            // Разбиваем на чанки по 100
-            for (chunk_idx, chunk) in ids.chunks(100).enumerate() {
-                set_current_operation.set(Some((chunk_idx * 100 + chunk.len(), total)));
-
-                let payload = json!({ "ids": chunk });
-                let response = Request::post(&format!("{}/api/a013/ym-order/batch-post", api_base()))
-                    .header("Content-Type", "application/json")
-                    .body(serde_json::to_string(&payload).unwrap_or_default())
-                    .map(|req| req.send());
-
-                match response {
-                    Ok(future) => match future.await {
-                        Ok(resp) => {
-                            if resp.status() == 200 {
-                                for id in chunk {
-                                    results.push((id.clone(), true, None));
-                                }
-                            } else {
-                                for id in chunk {
-                                    results.push((
-                                        id.clone(),
-                                        false,
-                                        Some(format!("HTTP {}", resp.status())),
-                                    ));
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            for id in chunk {
-                                results.push((id.clone(), false, Some(format!("{:?}", e))));
-                            }
-                        }
-                    },
-                    Err(e) => {
-                        for id in chunk {
-                            results.push((id.clone(), false, Some(format!("{:?}", e))));
-                        }
-                    }
-                }
-            }
+            let request = OperationRequest::PostDocuments {
+                entity_type: "a013_ym_order".to_string(),
+                ids: ids.into_iter().collect(),
+            };
+            run_documents_operation(request, set_current_operation).await;
 
-            set_operation_results.set(results);
             set_posting_in_progress.set(false);
             set_current_operation.set(None);
             state.update(|s| s.selected_ids.clear());
@@ -417,56 +378,15 @@ pub fn YmOrderList() -> impl IntoView {
         }
 
         set_posting_in_progress.set(true);
-        set_operation_results.set(Vec::new());
         set_current_operation.set(Some((0, ids.len())));
 
         spawn_local(async move {
-            let mut results = Vec::new();
-            let total = ids.len();
-
-            // Разбиваем на чанки по 100
-            for (chunk_idx, chunk) in ids.chunks(100).enumerate() {
-                set_current_operation.set(Some((chunk_idx * 100 + chunk.len(), total)));
-
-                let payload = json!({ "ids": chunk });
-                let response =
-                    Request::post(&format!("{}/api/a013/ym-order/batch-unpost", api_base()))
-                        .header("Content-Type", "application/json")
-                        .body(serde_json::to_string(&payload).unwrap_or_default())
-                        .map(|req| req.send());
-
-                match response {
-                    Ok(future) => match future.await {
-                        Ok(resp) => {
-                            if resp.status() == 200 {
-                                for id in chunk {
-                                    results.push((id.clone(), true, None));
-                                }
-                            } else {
-                                for id in chunk {
-                                    results.push((
-                                        id.clone(),
-                                        false,
-                                        Some(format!("HTTP {}", resp.status())),
-                                    ));
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            for id in chunk {
-                                results.push((id.clone(), false, Some(format!("{:?}", e))));
-                            }
-                        }
-                    },
-                    Err(e) => {
-                        for id in chunk {
-                            results.push((id.clone(), false, Some(format!("{:?}", e))));
-                        }
-                    }
-                }
-            }
+            let request = OperationRequest::UnpostDocuments {
+                entity_type: "a013_ym_order".to_string(),
+                ids: ids.into_iter().collect(),
+            };
+            run_documents_operation(request, set_current_operation).await;
 
-            set_operation_results.set(results);
             set_posting_in_progress.set(false);
             set_current_operation.set(None);
             state.update(|s| s.selected_ids.clear());
@@ -1,4 +1,4 @@
-use gloo_net::http::Request;
+use contracts::domain::common::VersionConflict;
 use leptos::logging::log;
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -6,8 +6,13 @@ use serde::{Deserialize, Serialize};
 // Import posting detail components
 use crate::domain::a010_ozon_fbs_posting::ui::details::OzonFbsPostingDetail;
 use crate::domain::a011_ozon_fbo_posting::ui::details::OzonFboPostingDetail;
-use crate::shared::api_utils::api_base;
+use crate::shared::api_client::{self, ApiError};
+use crate::shared::components::version_conflict_dialog::VersionConflictDialog;
 use crate::shared::date_utils::format_datetime_space as format_datetime;
+use crate::shared::money_format::{
+    amount_class, format_money, format_money_rub, format_money_rub_opt, format_number,
+    format_ratio_percent,
+};
 use crate::shared::page_frame::PageFrame;
 
 // DTO структуры для детального представления
@@ -30,6 +35,8 @@ pub struct OzonTransactionsDetailDto {
     pub created_at: String,
     #[serde(rename = "updated_at")]
     pub updated_at: String,
+    #[serde(default)]
+    pub version: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,6 +81,14 @@ pub struct ServiceDto {
     pub price: f64,
 }
 
+async fn fetch_detail(id: &str) -> Result<OzonTransactionsDetailDto, ApiError> {
+    api_client::get_json(&format!("/api/ozon_transactions/{}", id)).await
+}
+
+async fn fetch_projections(id: &str) -> Result<serde_json::Value, ApiError> {
+    api_client::get_json(&format!("/api/a014/ozon-transactions/{}/projections", id)).await
+}
+
 #[component]
 pub fn OzonTransactionsDetail(
     transaction_id: String,
@@ -87,6 +102,8 @@ pub fn OzonTransactionsDetail(
     let (posting, set_posting) = signal(false);
     let (projections, set_projections) = signal::<Option<serde_json::Value>>(None);
     let (projections_loading, set_projections_loading) = signal(false);
+    // 409 на проведении/отмене: транзакцию изменили после загрузки
+    let version_conflict = RwSignal::new(None::<VersionConflict>);
 
     // Signal for selected posting document (type, id)
     let (selected_posting, set_selected_posting) = signal::<Option<(String, String)>>(None);
@@ -106,75 +123,24 @@ pub fn OzonTransactionsDetail(
             set_loading.set(true);
             set_error.set(None);
 
-            let url = format!("{}/api/ozon_transactions/{}", api_base(), id);
-
-            match Request::get(&url).send().await {
-                Ok(response) => {
-                    let status = response.status();
-                    if status == 200 {
-                        match response.text().await {
-                            Ok(text) => {
-                                match serde_json::from_str::<OzonTransactionsDetailDto>(&text) {
-                                    Ok(data) => {
-                                        let transaction_id = data.id.clone();
-                                        set_transaction_data.set(Some(data));
-                                        set_loading.set(false);
-
-                                        // Асинхронная загрузка проекций
-                                        let set_projections = set_projections.clone();
-                                        let set_projections_loading =
-                                            set_projections_loading.clone();
-                                        wasm_bindgen_futures::spawn_local(async move {
-                                            set_projections_loading.set(true);
-                                            let projections_url = format!(
-                                                "{}/api/a014/ozon-transactions/{}/projections",
-                                                api_base(),
-                                                transaction_id
-                                            );
-                                            match Request::get(&projections_url).send().await {
-                                                Ok(resp) => {
-                                                    if resp.status() == 200 {
-                                                        if let Ok(text) = resp.text().await {
-                                                            if let Ok(proj_data) =
-                                                                serde_json::from_str::<
-                                                                    serde_json::Value,
-                                                                >(
-                                                                    &text
-                                                                )
-                                                            {
-                                                                set_projections
-                                                                    .set(Some(proj_data));
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                                Err(e) => {
-                                                    log!("Failed to load projections: {:?}", e);
-                                                }
-                                            }
-                                            set_projections_loading.set(false);
-                                        });
-                                    }
-                                    Err(e) => {
-                                        log!("Failed to parse transaction detail: {:?}", e);
-                                        set_error.set(Some(format!("Ошибка парсинга: {}", e)));
-                                        set_loading.set(false);
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                log!("Failed to read response: {:?}", e);
-                                set_error.set(Some(format!("Ошибка чтения ответа: {}", e)));
-                                set_loading.set(false);
-                            }
+            match fetch_detail(&id).await {
+                Ok(data) => {
+                    let transaction_id = data.id.clone();
+                    set_transaction_data.set(Some(data));
+                    set_loading.set(false);
+
+                    // Асинхронная загрузка проекций
+                    wasm_bindgen_futures::spawn_local(async move {
+                        set_projections_loading.set(true);
+                        match fetch_projections(&transaction_id).await {
+                            Ok(proj_data) => set_projections.set(Some(proj_data)),
+                            Err(e) => log!("Failed to load projections: {}", e),
                         }
-                    } else {
-                        set_error.set(Some(format!("Ошибка сервера: {}", status)));
-                        set_loading.set(false);
-                    }
+                        set_projections_loading.set(false);
+                    });
                 }
                 Err(e) => {
-                    log!("Failed to fetch transaction: {:?}", e);
+                    log!("Failed to fetch transaction: {}", e);
                     set_error.set(Some(format!("Ошибка загрузки: {}", e)));
                     set_loading.set(false);
                 }
@@ -182,8 +148,21 @@ pub fn OzonTransactionsDetail(
         });
     });
 
+    let on_conflict_reload = Callback::new(move |_: ()| {
+        let doc_id = stored_id.get_value();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Ok(data) = fetch_detail(&doc_id).await {
+                set_transaction_data.set(Some(data));
+            }
+            if let Ok(proj_data) = fetch_projections(&doc_id).await {
+                set_projections.set(Some(proj_data));
+            }
+        });
+    });
+
     view! {
         <PageFrame page_id="a014_ozon_transactions--detail" category="detail">
+            <VersionConflictDialog conflict=version_conflict on_reload=on_conflict_reload />
             <div class="page__header">
                 <div class="page__header-left">
                     <h2>
@@ -215,41 +194,27 @@ pub fn OzonTransactionsDetail(
                                             class="button button--warning"
                                             on:click=move |_| {
                                                 let doc_id = stored_id.get_value();
+                                                let version = transaction_data.get_untracked().map(|d| d.version);
                                                 set_posting.set(true);
                                                 wasm_bindgen_futures::spawn_local(async move {
-                                                    let url = format!("{}/api/a014/ozon-transactions/{}/unpost", api_base(), doc_id);
-                                                    match Request::post(&url).send().await {
-                                                        Ok(response) => {
-                                                            if response.status() == 200 {
-                                                                log!("Transaction unposted successfully");
-                                                                // Reload transaction data
-                                                                let reload_url = format!("{}/api/ozon_transactions/{}", api_base(), doc_id);
-                                                                if let Ok(resp) = Request::get(&reload_url).send().await {
-                                                                    if let Ok(text) = resp.text().await {
-                                                                        if let Ok(data) = serde_json::from_str::<OzonTransactionsDetailDto>(&text) {
-                                                                            log!("Reloaded transaction, is_posted: {}", data.is_posted);
-                                                                            set_transaction_data.set(Some(data));
-                                                                        }
-                                                                    }
-                                                                }
-                                                                // Reload projections
-                                                                let projections_url = format!("{}/api/a014/ozon-transactions/{}/projections", api_base(), doc_id);
-                                                                if let Ok(resp) = Request::get(&projections_url).send().await {
-                                                                    if resp.status() == 200 {
-                                                                        if let Ok(text) = resp.text().await {
-                                                                            if let Ok(proj_data) = serde_json::from_str::<serde_json::Value>(&text) {
-                                                                                set_projections.set(Some(proj_data));
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            } else {
-                                                                log!("Failed to unpost: status {}", response.status());
+                                                    let path = format!("/api/a014/ozon-transactions/{}/unpost", doc_id);
+                                                    match api_client::post_empty(&api_client::with_version(&path, version)).await {
+                                                        Ok(()) => {
+                                                            log!("Transaction unposted successfully");
+                                                            // Reload transaction data
+                                                            if let Ok(data) = fetch_detail(&doc_id).await {
+                                                                log!("Reloaded transaction, is_posted: {}", data.is_posted);
+                                                                set_transaction_data.set(Some(data));
+                                                            }
+                                                            // Reload projections
+                                                            if let Ok(proj_data) = fetch_projections(&doc_id).await {
+                                                                set_projections.set(Some(proj_data));
                                                             }
                                                         }
-                                                        Err(e) => {
-                                                            log!("Error unposting: {:?}", e);
-                                                        }
+                                                        Err(e) => match e.version_conflict() {
+                                                            Some(conflict) => version_conflict.set(Some(conflict)),
+                                                            None => log!("Failed to unpost: {}", e),
+                                                        },
                                                     }
                                                     set_posting.set(false);
                                                 });
@@ -267,41 +232,27 @@ pub fn OzonTransactionsDetail(
                                             class="button button--primary"
                                             on:click=move |_| {
                                                 let doc_id = stored_id.get_value();
+                                                let version = transaction_data.get_untracked().map(|d| d.version);
                                                 set_posting.set(true);
                                                 wasm_bindgen_futures::spawn_local(async move {
-                                                    let url = format!("{}/api/a014/ozon-transactions/{}/post", api_base(), doc_id);
-                                                    match Request::post(&url).send().await {
-                                                        Ok(response) => {
-                                                            if response.status() == 200 {
-                                                                log!("Transaction posted successfully");
-                                                                // Reload transaction data
-                                                                let reload_url = format!("{}/api/ozon_transactions/{}", api_base(), doc_id);
-                                                                if let Ok(resp) = Request::get(&reload_url).send().await {
-                                                                    if let Ok(text) = resp.text().await {
-                                                                        if let Ok(data) = serde_json::from_str::<OzonTransactionsDetailDto>(&text) {
-                                                                            log!("Reloaded transaction, is_posted: {}", data.is_posted);
-                                                                            set_transaction_data.set(Some(data));
-                                                                        }
-                                                                    }
-                                                                }
-                                                                // Reload projections
-                                                                let projections_url = format!("{}/api/a014/ozon-transactions/{}/projections", api_base(), doc_id);
-                                                                if let Ok(resp) = Request::get(&projections_url).send().await {
-                                                                    if resp.status() == 200 {
-                                                                        if let Ok(text) = resp.text().await {
-                                                                            if let Ok(proj_data) = serde_json::from_str::<serde_json::Value>(&text) {
-                                                                                set_projections.set(Some(proj_data));
-                                                                            }
-                                                                        }
-                                                                    }
-                                                                }
-                                                            } else {
-                                                                log!("Failed to post: status {}", response.status());
+                                                    let path = format!("/api/a014/ozon-transactions/{}/post", doc_id);
+                                                    match api_client::post_empty(&api_client::with_version(&path, version)).await {
+                                                        Ok(()) => {
+                                                            log!("Transaction posted successfully");
+                                                            // Reload transaction data
+                                                            if let Ok(data) = fetch_detail(&doc_id).await {
+                                                                log!("Reloaded transaction, is_posted: {}", data.is_posted);
+                                                                set_transaction_data.set(Some(data));
+                                                            }
+                                                            // Reload projections
+                                                            if let Ok(proj_data) = fetch_projections(&doc_id).await {
+                                                                set_projections.set(Some(proj_data));
                                                             }
                                                         }
-                                                        Err(e) => {
-                                                            log!("Error posting: {:?}", e);
-                                                        }
+                                                        Err(e) => match e.version_conflict() {
+                                                            Some(conflict) => version_conflict.set(Some(conflict)),
+                                                            None => log!("Failed to post: {}", e),
+                                                        },
                                                     }
                                                     set_posting.set(false);
                                                 });
@@ -431,7 +382,7 @@ pub fn OzonTransactionsDetail(
                                                                             "color: var(--color-error);"
                                                                         }
                                                                     )>
-                                                                        {format!("{:.2} ₽", data.header.amount)}
+                                                                        {format_money_rub(data.header.amount)}
                                                                     </span>
                                                                 </div>
 
@@ -440,21 +391,21 @@ pub fn OzonTransactionsDetail(
                                                                     <span class="field-label-tech">"/ accruals_for_sale"</span>
                                                                     ":"
                                                                 </div>
-                                                                <div class="field-value">{format!("{:.2} ₽", data.header.accruals_for_sale)}</div>
+                                                                <div class="field-value">{format_money_rub(data.header.accruals_for_sale)}</div>
 
                                                                 <div style="font-weight: var(--font-weight-semibold); color: var(--color-text-secondary); font-size: var(--font-size-sm);">
                                                                     "Комиссия за продажу "
                                                                     <span class="field-label-tech">"/ sale_commission"</span>
                                                                     ":"
                                                                 </div>
-                                                                <div class="field-value">{format!("{:.2} ₽", data.header.sale_commission)}</div>
+                                                                <div class="field-value">{format_money_rub(data.header.sale_commission)}</div>
 
                                                                 <div style="font-weight: var(--font-weight-semibold); color: var(--color-text-secondary); font-size: var(--font-size-sm);">
                                                                     "Стоимость доставки "
                                                                     <span class="field-label-tech">"/ delivery_charge"</span>
                                                                     ":"
                                                                 </div>
-                                                                <div class="field-value">{format!("{:.2} ₽", data.header.delivery_charge)}</div>
+                                                                <div class="field-value">{format_money_rub(data.header.delivery_charge)}</div>
                                                             </div>
                                                         </div>
 
@@ -547,10 +498,10 @@ pub fn OzonTransactionsDetail(
                                                                                 <td class="field-value-mono" style="padding: var(--spacing-sm); color: var(--color-text-primary);">{item.sku}</td>
                                                                                 <td style="padding: var(--spacing-sm); color: var(--color-text-primary);">{item.name.clone()}</td>
                                                                                 <td style="padding: var(--spacing-sm); text-align: right; color: var(--color-text-primary);">
-                                                                                    {item.price.map(|p| format!("{:.2} ₽", p)).unwrap_or("—".to_string())}
+                                                                                    {format_money_rub_opt(item.price)}
                                                                                 </td>
                                                                                 <td style="padding: var(--spacing-sm); text-align: right; color: var(--color-text-secondary);">
-                                                                                    {item.ratio.map(|r| format!("{:.1}%", r * 100.0)).unwrap_or("—".to_string())}
+                                                                                    {item.ratio.map(|r| format_ratio_percent(r, 1)).unwrap_or_else(|| "—".to_string())}
                                                                                 </td>
                                                                                 <td class="field-value-mono-sm" style="padding: var(--spacing-sm);" title={item.marketplace_product_ref.clone().unwrap_or_default()}>
                                                                                     {item.marketplace_product_ref.as_ref().map(|r| r.clone()).unwrap_or("—".to_string())}
@@ -586,7 +537,7 @@ pub fn OzonTransactionsDetail(
                                                                         {data.services.iter().map(|service| view! {
                                                                             <tr style="border-bottom: 1px solid var(--color-border-light);">
                                                                                 <td style="padding: var(--spacing-sm); color: var(--color-text-primary);">{service.name.clone()}</td>
-                                                                                <td style="padding: var(--spacing-sm); text-align: right; font-weight: var(--font-weight-semibold); color: var(--color-success);">{format!("{:.2} ₽", service.price)}</td>
+                                                                                <td style="padding: var(--spacing-sm); text-align: right; font-weight: var(--font-weight-semibold); color: var(--color-success);">{format_money_rub(service.price)}</td>
                                                                             </tr>
                                                                         }).collect::<Vec<_>>()}
                                                                     </tbody>
@@ -643,7 +594,7 @@ pub fn OzonTransactionsDetail(
                                                                                                             <td class="field-value-mono" style="padding: var(--spacing-xs) var(--spacing-xs); border: 1px solid var(--color-border);">{sku}</td>
                                                                                                             <td style="padding: var(--spacing-xs) var(--spacing-xs); border: 1px solid var(--color-border);">{title}</td>
                                                                                                             <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{qty}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border); font-weight: var(--font-weight-semibold);">{format!("{:.2}", amount)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border); font-weight: var(--font-weight-semibold);">{format_money(amount)}</td>
                                                                                                         </tr>
                                                                                                     }
                                                                                                 }).collect::<Vec<_>>()}
@@ -687,7 +638,7 @@ pub fn OzonTransactionsDetail(
                                                                                                             <td class="field-value-mono" style="padding: var(--spacing-xs) var(--spacing-xs); border: 1px solid var(--color-border);">{posting}</td>
                                                                                                             <td class="field-value-mono" style="padding: var(--spacing-xs) var(--spacing-xs); border: 1px solid var(--color-border);">{sku}</td>
                                                                                                             <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{qty}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border); font-weight: var(--font-weight-semibold);">{format!("{:.2}", amount)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border); font-weight: var(--font-weight-semibold);">{format_money(amount)}</td>
                                                                                                         </tr>
                                                                                                     }
                                                                                                 }).collect::<Vec<_>>()}
@@ -760,20 +711,20 @@ pub fn OzonTransactionsDetail(
                                                                                                             <td class="field-value-mono" style="padding: var(--spacing-xs) var(--spacing-xs); border: 1px solid var(--color-border);">{document_no}</td>
                                                                                                             <td class="field-value-mono" style="padding: var(--spacing-xs) var(--spacing-xs); border: 1px solid var(--color-border);">{article}</td>
                                                                                                             <td style="padding: var(--spacing-xs) var(--spacing-xs); border: 1px solid var(--color-border);">{connection_mp_ref}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format!("{:.2}", customer_in)}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format!("{:.2}", customer_out)}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format!("{:.2}", coinvest_in)}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format!("{:.2}", commission_out)}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format!("{:.2}", acquiring_out)}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format!("{:.2}", penalty_out)}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format!("{:.2}", logistics_out)}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format!("{:.2}", seller_out)}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format!("{:.2}", price_full)}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format!("{:.2}", price_list)}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format!("{:.2}", price_return)}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format!("{:.2}", commission_percent)}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format!("{:.2}", coinvest_persent)}</td>
-                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border); font-weight: var(--font-weight-semibold);">{format!("{:.2}", total)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format_money(customer_in)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format_money(customer_out)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format_money(coinvest_in)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format_money(commission_out)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format_money(acquiring_out)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format_money(penalty_out)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format_money(logistics_out)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format_money(seller_out)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format_money(price_full)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format_money(price_list)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format_money(price_return)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format_number(commission_percent, 2)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border);">{format_number(coinvest_persent, 2)}</td>
+                                                                                                            <td style="padding: var(--spacing-xs) var(--spacing-xs); text-align: right; border: 1px solid var(--color-border); font-weight: var(--font-weight-semibold);" class=amount_class(total)>{format_money(total)}</td>
                                                                                                         </tr>
                                                                                                     }
                                                                                                 }).collect::<Vec<_>>()}
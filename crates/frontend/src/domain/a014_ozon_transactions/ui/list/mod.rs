@@ -3,9 +3,13 @@ pub mod state;
 use self::state::create_state;
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::api_utils::api_base;
+use crate::shared::auth_download::download_authenticated_file;
 use crate::shared::components::date_input::DateInput;
 use crate::shared::components::month_selector::MonthSelector;
 use crate::shared::components::pagination_controls::PaginationControls;
+use crate::shared::components::row_context_menu::{
+    api_url, RowAction, RowContextMenu, RowMenuState, RowRef,
+};
 use crate::shared::components::table::{
     TableCellCheckbox, TableCellMoney, TableCrosshairHighlight, TableHeaderCheckbox,
 };
@@ -24,7 +28,6 @@ use std::cmp::Ordering;
 use thaw::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
 
 use crate::shared::page_standard::PAGE_CAT_LIST;
 
@@ -290,6 +293,32 @@ pub fn OzonTransactionsList() -> impl IntoView {
         );
     };
 
+    // Контекстное меню строк (правый клик)
+    let row_menu = RowMenuState::new();
+    let row_actions = vec![
+        RowAction::new("Открыть", "eye", move |row: RowRef| {
+            tabs_store.open_tab(
+                &format!("a014_ozon_transactions_details_{}", row.id),
+                &format!("Транзакция OZON #{}", row.title),
+            );
+        }),
+        RowAction::post_to(
+            "Провести",
+            "check",
+            |id| api_url(&format!("/api/a014/ozon-transactions/{}/post", id)),
+            load_transactions,
+        )
+        .write("a014_ozon_transactions"),
+        RowAction::post_to(
+            "Отменить проведение",
+            "x",
+            |id| api_url(&format!("/api/a014/ozon-transactions/{}/unpost", id)),
+            load_transactions,
+        )
+        .write("a014_ozon_transactions"),
+        RowAction::copy_id(),
+    ];
+
     let post_batch = move |post: bool| {
         let ids: Vec<String> = state.with_untracked(|s| s.selected_ids.iter().cloned().collect());
         if ids.is_empty() {
@@ -481,10 +510,21 @@ pub fn OzonTransactionsList() -> impl IntoView {
                     <Button
                         appearance=ButtonAppearance::Secondary
                         on_click=move |_| {
-                            let data = all_transactions.get_untracked();
-                            if let Err(e) = export_to_csv(&data) {
-                                log!("Failed to export: {}", e);
-                            }
+                            let url = state.with_untracked(|s| {
+                                export_url(
+                                    &s.date_from,
+                                    &s.date_to,
+                                    &s.transaction_type_filter,
+                                    &s.operation_type_name_filter,
+                                    &s.posting_number_filter,
+                                )
+                            });
+                            spawn_local(async move {
+                                if let Err(e) = download_authenticated_file(&url, "ozon_transactions.csv").await {
+                                    log!("Failed to export: {}", e);
+                                    set_error.set(Some(format!("Ошибка экспорта: {}", e)));
+                                }
+                            });
                         }
                         disabled=Signal::derive(move || loading.get() || all_transactions.get().is_empty())
                     >
@@ -795,8 +835,9 @@ pub fn OzonTransactionsList() -> impl IntoView {
                                     let formatted_date = format_date(&item.operation_date);
                                     let delivering = item.delivering_date.as_deref().map(format_date).unwrap_or_default();
                                     let substatus_display = item.substatus.clone().unwrap_or_default();
+                                    let row_ref = RowRef::new(item.id.clone(), operation_id.to_string());
                                     view! {
-                                        <TableRow>
+                                        <TableRow on:contextmenu=move |ev| row_menu.open_at(&ev, row_ref.clone())>
                                             <TableCellCheckbox
                                                 item_id=item_id.clone()
                                                 selected=selected_signal
@@ -882,6 +923,7 @@ pub fn OzonTransactionsList() -> impl IntoView {
                     </Table>
                 </div>
             </div>
+            <RowContextMenu state=row_menu actions=row_actions />
         </PageFrame>
     }
 }
@@ -953,57 +995,34 @@ async fn save_settings_to_database(
     Ok(())
 }
 
-fn export_to_csv(data: &[OzonTransactionsDto]) -> Result<(), String> {
-    let mut csv = String::from("\u{FEFF}");
-    csv.push_str("Date;Operation ID;Operation Type;Substatus;Delivering Date;Posting Number;Transaction Type;Delivery Schema;Amount;Accruals;Commission;Delivery;Post\n");
-    for txn in data {
-        let op_date = format_date(&txn.operation_date);
-        let substatus = txn.substatus.as_deref().unwrap_or("");
-        let delivering_date = txn
-            .delivering_date
-            .as_deref()
-            .map(format_date)
-            .unwrap_or_default();
-        let status = if txn.is_posted { "Да" } else { "Нет" };
-        csv.push_str(&format!(
-            "\"{}\";{};\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";{};{};{};{};\"{}\"
-",
-            op_date,
-            txn.operation_id,
-            txn.operation_type_name.replace('\"', "\"\""),
-            substatus,
-            delivering_date,
-            txn.posting_number.replace('\"', "\"\""),
-            txn.transaction_type.replace('\"', "\"\""),
-            txn.delivery_schema.replace('\"', "\"\""),
-            format!("{:.2}", txn.amount).replace(".", ","),
-            format!("{:.2}", txn.accruals_for_sale).replace(".", ","),
-            format!("{:.2}", txn.sale_commission).replace(".", ","),
-            format!("{:.2}", txn.delivery_charge).replace(".", ","),
-            status
+/// URL серверной CSV-выгрузки по текущим фильтрам списка.
+fn export_url(
+    date_from: &str,
+    date_to: &str,
+    transaction_type: &str,
+    operation_type_name: &str,
+    posting_number: &str,
+) -> String {
+    let mut url = format!(
+        "{}/api/a014/ozon-transactions/export?format=csv&date_from={}&date_to={}",
+        api_base(),
+        date_from,
+        date_to
+    );
+    if !transaction_type.is_empty() {
+        url.push_str(&format!("&transaction_type={}", transaction_type));
+    }
+    if !operation_type_name.is_empty() {
+        url.push_str(&format!(
+            "&operation_type_name={}",
+            urlencoding::encode(operation_type_name)
         ));
     }
-    let blob_parts = js_sys::Array::new();
-    blob_parts.push(&wasm_bindgen::JsValue::from_str(&csv));
-    let blob_props = BlobPropertyBag::new();
-    blob_props.set_type("text/csv;charset=utf-8;");
-    let blob = Blob::new_with_str_sequence_and_options(&blob_parts, &blob_props)
-        .map_err(|e| format!("Failed to create blob: {:?}", e))?;
-    let url = Url::create_object_url_with_blob(&blob)
-        .map_err(|e| format!("Failed to create URL: {:?}", e))?;
-    let window = web_sys::window().ok_or_else(|| "no window".to_string())?;
-    let document = window.document().ok_or_else(|| "no document".to_string())?;
-    let a = document
-        .create_element("a")
-        .map_err(|e| format!("{:?}", e))?
-        .dyn_into::<HtmlAnchorElement>()
-        .map_err(|e| format!("{:?}", e))?;
-    a.set_href(&url);
-    a.set_download(&format!(
-        "ozon_transactions_{}.csv",
-        chrono::Utc::now().format("%Y%m%d_%H%M%S")
-    ));
-    a.click();
-    Url::revoke_object_url(&url).map_err(|e| format!("Failed to revoke URL: {:?}", e))?;
-    Ok(())
+    if !posting_number.is_empty() {
+        url.push_str(&format!(
+            "&posting_number={}",
+            urlencoding::encode(posting_number)
+        ));
+    }
+    url
 }
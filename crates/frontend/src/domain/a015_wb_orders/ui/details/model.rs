@@ -1,8 +1,10 @@
 //! API layer for WB Orders details
 
-use crate::shared::api_utils::api_base;
-use contracts::projections::p903_wb_finance_report::dto::WbFinanceReportDto;
-use gloo_net::http::Request;
+use crate::shared::api_client::{self, ApiError};
+use contracts::projections::p903_wb_finance_report::dto::{
+    WbFinanceReportDto, WbFinanceReportSridTotals,
+};
+use contracts::shared::marketplace_links::MarketplaceLinkDto;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,9 +19,13 @@ pub struct WbOrderDetailDto {
     pub geography: GeographyDto,
     pub source_meta: SourceMetaDto,
     pub metadata: MetadataDto,
+    #[serde(default)]
+    pub is_sandbox: bool,
     pub marketplace_product_ref: Option<String>,
     pub nomenclature_ref: Option<String>,
     pub base_nomenclature_ref: Option<String>,
+    #[serde(default)]
+    pub marketplace_links: Vec<MarketplaceLinkDto>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,6 +89,9 @@ pub struct SourceMetaDto {
     pub marketplace_raw_payload_ref: Option<String>,
     pub fetched_at: String,
     pub document_version: i32,
+    /// Сессия импорта u504 — ключ раздела «Происхождение импорта»
+    #[serde(default)]
+    pub import_session_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -162,297 +171,111 @@ pub struct WbSalesStateDto {
     pub event_type: String,
 }
 
-pub async fn fetch_by_id(id: &str) -> Result<WbOrderDetailDto, String> {
-    let url = format!("{}/api/a015/wb-orders/{}", api_base(), id);
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
+/// Строковое поле из JSON справочника; отсутствующее — пустая строка.
+fn str_field(json: &serde_json::Value, key: &str) -> String {
+    json.get(key)
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string()
+}
 
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
-    serde_json::from_str(&text).map_err(|e| format!("Failed to parse: {}", e))
+pub async fn fetch_by_id(id: &str) -> Result<WbOrderDetailDto, String> {
+    Ok(api_client::get_json(&format!("/api/a015/wb-orders/{}", id)).await?)
 }
 
 /// Движения проекций документа (p909 + p916) как raw JSON для закладки «Проекции».
 pub async fn fetch_projections(id: &str) -> Result<serde_json::Value, String> {
-    let url = format!("{}/api/a015/wb-orders/{}/projections", api_base(), id);
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch projections: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
-    serde_json::from_str(&text).map_err(|e| format!("Failed to parse JSON: {}", e))
+    Ok(api_client::get_json(&format!("/api/a015/wb-orders/{}/projections", id)).await?)
 }
 
 pub async fn fetch_raw_json(raw_payload_ref: &str) -> Result<String, String> {
-    let url = format!("{}/api/a015/raw/{}", api_base(), raw_payload_ref);
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch raw JSON: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
     let json_value: serde_json::Value =
-        serde_json::from_str(&text).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+        api_client::get_json(&format!("/api/a015/raw/{}", raw_payload_ref)).await?;
     serde_json::to_string_pretty(&json_value).map_err(|e| format!("Failed to format JSON: {}", e))
 }
 
 pub async fn fetch_finance_reports(srid: &str) -> Result<Vec<WbFinanceReportDto>, String> {
-    let url = format!(
-        "{}/api/p903/finance-report/search-by-srid?srid={}",
-        api_base(),
+    Ok(api_client::get_json(&format!(
+        "/api/p903/finance-report/search-by-srid?srid={}",
         srid
-    );
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch finance reports: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
+    ))
+    .await?)
+}
 
-    response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse finance reports: {}", e))
+pub async fn fetch_finance_reports_totals(srid: &str) -> Result<WbFinanceReportSridTotals, String> {
+    Ok(api_client::get_json(&format!(
+        "/api/p903/finance-report/search-by-srid/totals?srid={}",
+        srid
+    ))
+    .await?)
 }
 
 pub async fn fetch_wb_sales(document_no: &str) -> Result<Vec<WbSalesListItemDto>, String> {
-    let encoded_document_no = urlencoding::encode(document_no);
-    let url = format!(
-        "{}/api/a012/wb-sales/search-by-srid?srid={}",
-        api_base(),
-        encoded_document_no
-    );
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch wb sales: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse wb sales: {}", e))
+    Ok(api_client::get_json(&format!(
+        "/api/a012/wb-sales/search-by-srid?srid={}",
+        urlencoding::encode(document_no)
+    ))
+    .await?)
 }
 
 pub async fn fetch_supply_for_order(order_id: &str) -> Result<Option<WbSupplyLinkInfo>, String> {
-    let url = format!("{}/api/a029/wb-supply/by-order/{}", api_base(), order_id);
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch supply link: {}", e))?;
-
-    if response.status() == 404 {
-        return Ok(None);
-    }
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    response
-        .json()
-        .await
-        .map(Some)
-        .map_err(|e| format!("Failed to parse supply link: {}", e))
+    Ok(api_client::get_json_opt(&format!("/api/a029/wb-supply/by-order/{}", order_id)).await?)
 }
 
 pub async fn fetch_marketplace_product(id: &str) -> Result<MarketplaceProductInfo, String> {
-    let url = format!("{}/api/marketplace_product/{}", api_base(), id);
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch marketplace product: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
     let json: serde_json::Value =
-        serde_json::from_str(&text).map_err(|e| format!("Failed to parse: {}", e))?;
-
+        api_client::get_json(&format!("/api/marketplace_product/{}", id)).await?;
     Ok(MarketplaceProductInfo {
-        description: json
-            .get("description")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
-        article: json
-            .get("article")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
+        description: str_field(&json, "description"),
+        article: str_field(&json, "article"),
     })
 }
 
 pub async fn fetch_nomenclature(id: &str) -> Result<NomenclatureInfo, String> {
-    let url = format!("{}/api/nomenclature/{}", api_base(), id);
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch nomenclature: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
     let json: serde_json::Value =
-        serde_json::from_str(&text).map_err(|e| format!("Failed to parse: {}", e))?;
-
+        api_client::get_json(&format!("/api/nomenclature/{}", id)).await?;
     Ok(NomenclatureInfo {
-        description: json
-            .get("description")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
-        article: json
-            .get("article")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
+        description: str_field(&json, "description"),
+        article: str_field(&json, "article"),
     })
 }
 
-pub async fn post_document(id: &str) -> Result<(), String> {
-    let url = format!("{}/api/a015/wb-orders/{}/post", api_base(), id);
-    let response = Request::post(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to post document: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Failed to post: status {}", response.status()));
-    }
-
-    Ok(())
+pub async fn post_document(id: &str, version: Option<i32>) -> Result<(), ApiError> {
+    api_client::post_empty(&api_client::with_version(
+        &format!("/api/a015/wb-orders/{}/post", id),
+        version,
+    ))
+    .await
 }
 
 #[allow(dead_code)]
-pub async fn unpost_document(id: &str) -> Result<(), String> {
-    let url = format!("{}/api/a015/wb-orders/{}/unpost", api_base(), id);
-    let response = Request::post(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to unpost document: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Failed to unpost: status {}", response.status()));
-    }
-
-    Ok(())
+pub async fn unpost_document(id: &str, version: Option<i32>) -> Result<(), ApiError> {
+    api_client::post_empty(&api_client::with_version(
+        &format!("/api/a015/wb-orders/{}/unpost", id),
+        version,
+    ))
+    .await
 }
 
 pub async fn fetch_connection(id: &str) -> Result<ConnectionInfo, String> {
-    let url = format!("{}/api/connection_mp/{}", api_base(), id);
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch connection: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
     let json: serde_json::Value =
-        serde_json::from_str(&text).map_err(|e| format!("Failed to parse: {}", e))?;
-
+        api_client::get_json(&format!("/api/connection_mp/{}", id)).await?;
     Ok(ConnectionInfo {
-        description: json
-            .get("description")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
+        description: str_field(&json, "description"),
     })
 }
 
 pub async fn fetch_organization(id: &str) -> Result<OrganizationInfo, String> {
-    let url = format!("{}/api/organization/{}", api_base(), id);
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch organization: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
     let json: serde_json::Value =
-        serde_json::from_str(&text).map_err(|e| format!("Failed to parse: {}", e))?;
-
+        api_client::get_json(&format!("/api/organization/{}", id)).await?;
     Ok(OrganizationInfo {
-        description: json
-            .get("description")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
+        description: str_field(&json, "description"),
     })
 }
 
 pub async fn fetch_marketplace(id: &str) -> Result<MarketplaceInfo, String> {
-    let url = format!("{}/api/marketplace/{}", api_base(), id);
-    let response = Request::get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch marketplace: {}", e))?;
-
-    if response.status() != 200 {
-        return Err(format!("Server error: {}", response.status()));
-    }
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read response: {}", e))?;
-    let json: serde_json::Value =
-        serde_json::from_str(&text).map_err(|e| format!("Failed to parse: {}", e))?;
-
+    let json: serde_json::Value = api_client::get_json(&format!("/api/marketplace/{}", id)).await?;
     Ok(MarketplaceInfo {
-        name: json
-            .get("description")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
+        name: str_field(&json, "description"),
     })
 }
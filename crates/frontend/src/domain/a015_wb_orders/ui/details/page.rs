@@ -3,8 +3,14 @@
 use super::tabs::{GeneralTab, JsonTab, LineTab, LinksTab, ProjectionsTab, SalesTab};
 use super::view_model::WbOrdersDetailsVm;
 use crate::layout::global_context::AppGlobalContext;
+use crate::layout::tab_session;
+use crate::shared::components::marketplace_links::MarketplaceLinkButtons;
+use crate::shared::components::sandbox_badge::SandboxBadge;
+use crate::shared::components::version_conflict_dialog::VersionConflictDialog;
 use crate::shared::icons::icon;
 use crate::shared::page_frame::PageFrame;
+use crate::system::document_audit::ui::DocumentHistoryPanel;
+use crate::system::external_refs::ui::ExternalRefsPanel;
 use crate::system::favorites::ui::FavoriteButton;
 use leptos::prelude::*;
 use thaw::*;
@@ -17,6 +23,20 @@ pub fn WbOrdersDetails(id: String, #[prop(into)] on_close: Callback<()>) -> impl
     let stored_id = StoredValue::new(id.clone());
 
     vm.load(id);
+    tab_session::bind_sub_tab(
+        format!("a015_wb_orders_details_{}", stored_id.get_value()),
+        vm.active_tab,
+        &[
+            "general",
+            "line",
+            "json",
+            "links",
+            "projections",
+            "sales",
+            "external_refs",
+            "history",
+        ],
+    );
 
     Effect::new({
         let vm = vm.clone();
@@ -54,9 +74,15 @@ pub fn WbOrdersDetails(id: String, #[prop(into)] on_close: Callback<()>) -> impl
     let vm_header = vm.clone();
     let vm_tabs = vm.clone();
     let vm_content = vm.clone();
+    let version_conflict = vm.version_conflict;
+    let on_conflict_reload = {
+        let vm = vm.clone();
+        Callback::new(move |_: ()| vm.reload_document())
+    };
 
     view! {
         <PageFrame page_id="a015_wb_orders_details" category="detail">
+            <VersionConflictDialog conflict=version_conflict on_reload=on_conflict_reload />
             <Header
                 vm=vm_header
                 favorite_target_id=stored_id.get_value()
@@ -103,9 +129,12 @@ fn Header(
     on_close: Callback<()>,
 ) -> impl IntoView {
     let is_posted = vm.is_posted();
+    let is_sandbox = vm.is_sandbox();
     let document_no = vm.document_no();
     let title = Signal::derive(move || format!("WB Order {}", document_no.get()));
     let order = vm.order;
+    let marketplace_links =
+        Signal::derive(move || order.get().map(|o| o.marketplace_links).unwrap_or_default());
     let tab_key = format!("a015_wb_orders_details_{}", favorite_target_id);
 
     view! {
@@ -131,8 +160,11 @@ fn Header(
                         }
                     }}
                 </Show>
+                <SandboxBadge is_sandbox=is_sandbox />
             </div>
             <div class="page__header-right">
+                <MarketplaceLinkButtons links=marketplace_links />
+
                 <OrderFlowButton vm=vm.clone() />
 
                 <PostButtons vm=vm.clone() />
@@ -316,7 +348,10 @@ fn TabBar(vm: WbOrdersDetailsVm) -> impl IntoView {
             <button
                 class="page__tab"
                 class:page__tab--active=move || active_tab.get() == "sales"
-                on:click=move |_| vm.set_tab("sales")
+                on:click={
+                    let vm = vm.clone();
+                    move |_| vm.set_tab("sales")
+                }
             >
                 {icon("shopping-cart")} "Sales"
                 <Badge
@@ -334,6 +369,22 @@ fn TabBar(vm: WbOrdersDetailsVm) -> impl IntoView {
                     {move || wb_sales_count.get().to_string()}
                 </Badge>
             </button>
+
+            <button
+                class="page__tab"
+                class:page__tab--active=move || active_tab.get() == "external_refs"
+                on:click=move |_| vm.set_tab("external_refs")
+            >
+                {icon("link")} "Внешние ссылки"
+            </button>
+
+            <button
+                class="page__tab"
+                class:page__tab--active=move || active_tab.get() == "history"
+                on:click=move |_| vm.set_tab("history")
+            >
+                {icon("clock")} "История"
+            </button>
         </div>
     }
 }
@@ -347,6 +398,7 @@ fn TabContent(vm: WbOrdersDetailsVm) -> impl IntoView {
     let vm_links = vm.clone();
     let vm_sales = vm.clone();
     let vm_projections = vm.clone();
+    let document_id = vm.id;
 
     view! {
         {move || match active_tab.get() {
@@ -356,6 +408,8 @@ fn TabContent(vm: WbOrdersDetailsVm) -> impl IntoView {
             "links" => view! { <LinksTab vm=vm_links.clone() /> }.into_any(),
             "sales" => view! { <SalesTab vm=vm_sales.clone() /> }.into_any(),
             "projections" => view! { <ProjectionsTab vm=vm_projections.clone() /> }.into_any(),
+            "external_refs" => view! { <ExternalRefsPanel entity_type="a015_wb_orders" document_id=document_id /> }.into_any(),
+            "history" => view! { <DocumentHistoryPanel entity_type="a015_wb_orders" document_id=document_id /> }.into_any(),
             _ => view! { <GeneralTab vm=vm_general.clone() /> }.into_any(),
         }}
     }
@@ -3,6 +3,7 @@
 use super::super::view_model::WbOrdersDetailsVm;
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::components::card_animated::CardAnimated;
+use crate::shared::components::sku_sparkline::SkuSparkline;
 use crate::shared::components::ui::FieldDisplay;
 use crate::shared::date_utils::format_datetime_utc_local;
 use leptos::prelude::*;
@@ -95,6 +96,8 @@ pub fn GeneralTab(vm: WbOrdersDetailsVm) -> impl IntoView {
             let base_nom_ref = order_data.base_nomenclature_ref.clone();
             let mp_ref_click = mp_ref.clone();
             let mp_ref_text = mp_ref.clone();
+            let mp_ref_sparkline = mp_ref.clone();
+            let sparkline_date = order_data.state.order_dt.clone();
             let nom_ref_click = nom_ref.clone();
             let nom_ref_text = nom_ref.clone();
             let base_nom_ref_click = base_nom_ref.clone();
@@ -218,6 +221,9 @@ pub fn GeneralTab(vm: WbOrdersDetailsVm) -> impl IntoView {
                                             .unwrap_or_else(|| "Открыть".to_string())
                                     }}
                                 </a>
+                                {mp_ref_sparkline.map(|id| view! {
+                                    <SkuSparkline marketplace_product_ref=id date_to=sparkline_date />
+                                })}
                             </div>
                             <div class="form__group">
                                 <label class="form__label">"Номенклатура 1С"</label>
@@ -2,6 +2,8 @@
 
 use super::super::view_model::WbOrdersDetailsVm;
 use crate::shared::components::card_animated::CardAnimated;
+use crate::shared::components::import_provenance::ImportProvenanceSection;
+use crate::shared::components::raw_payload_versions::RawPayloadVersions;
 use crate::shared::json_viewer::widget::JsonViewer;
 use leptos::prelude::*;
 use thaw::*;
@@ -16,6 +18,8 @@ fn RawJsonCard(
     description: &'static str,
     loading: Signal<bool>,
     json: Signal<Option<String>>,
+    /// `raw_payload_ref` документа — для выбора прежних версий payload
+    raw_ref: Signal<Option<String>>,
     /// Текст «нет данных» (Marketplace отсутствует у FBW / отменённых FBS).
     empty_note: &'static str,
 ) -> impl IntoView {
@@ -25,6 +29,7 @@ fn RawJsonCard(
             <div style="margin-bottom: var(--spacing-sm); color: var(--color-text-secondary);">
                 <code>{endpoint}</code>" — "{description}
             </div>
+            <RawPayloadVersions raw_base="/api/a015/raw" current_ref=raw_ref current_json=json />
             {move || {
                 if loading.get() {
                     view! {
@@ -70,6 +75,30 @@ pub fn JsonTab(vm: WbOrdersDetailsVm) -> impl IntoView {
         let vm = vm.clone();
         move || vm.marketplace_raw_json.get()
     });
+    let stats_ref = Signal::derive({
+        let vm = vm.clone();
+        move || {
+            vm.order
+                .get()
+                .map(|order| order.source_meta.raw_payload_ref)
+        }
+    });
+    let mp_ref = Signal::derive({
+        let vm = vm.clone();
+        move || {
+            vm.order
+                .get()
+                .and_then(|order| order.source_meta.marketplace_raw_payload_ref)
+        }
+    });
+    let import_session_id = Signal::derive({
+        let vm = vm.clone();
+        move || {
+            vm.order
+                .get()
+                .and_then(|order| order.source_meta.import_session_id)
+        }
+    });
 
     view! {
         <div class="detail-grid">
@@ -81,6 +110,7 @@ pub fn JsonTab(vm: WbOrdersDetailsVm) -> impl IntoView {
                 description="Основной ответ WB по документу (суммы в рублях)."
                 loading=stats_loading
                 json=stats_json
+                raw_ref=stats_ref
                 empty_note="JSON данные не загружены"
             />
             <RawJsonCard
@@ -91,8 +121,14 @@ pub fn JsonTab(vm: WbOrdersDetailsVm) -> impl IntoView {
                 description="Сборочное задание FBS (валюта продажи в currencyCode). Отсутствует у FBW и отменённых FBS."
                 loading=mp_loading
                 json=mp_json
+                raw_ref=mp_ref
                 empty_note="Marketplace API payload отсутствует для этого заказа"
             />
+            {move || import_session_id.get().map(|session_id| view! {
+                <CardAnimated delay_ms=80 nav_id="a015_wb_orders_details_json_provenance">
+                    <ImportProvenanceSection session_id=session_id aggregate_index="a015_wb_orders" />
+                </CardAnimated>
+            })}
         </div>
     }
 }
@@ -4,6 +4,7 @@ use super::super::view_model::WbOrdersDetailsVm;
 use crate::shared::components::card_animated::CardAnimated;
 use crate::shared::components::popover::HelpPopoverLabel;
 use crate::shared::components::table::TableCellMoney;
+use crate::shared::money_format::{amount_class, format_money, format_money_opt};
 use leptos::prelude::*;
 use serde_json::Value;
 use thaw::*;
@@ -52,13 +53,6 @@ fn currency_label(code: i64) -> &'static str {
     }
 }
 
-fn format_money(value: Option<f64>) -> String {
-    match value {
-        Some(v) => format!("{:.2}", v),
-        None => "-".to_string(),
-    }
-}
-
 #[component]
 fn PriceMetricLabel(
     label: &'static str,
@@ -201,10 +195,10 @@ pub fn LineTab(vm: WbOrdersDetailsVm) -> impl IntoView {
                                 <Badge appearance=BadgeAppearance::Tint color=BadgeColor::Brand>
                                     {format!("Записей: {}", reports.len())}
                                 </Badge>
-                                <span>"PPVZ VW: " <strong>{format!("{:.2}", total_ppvz_vw)}</strong></span>
-                                <span>"PPVZ VW NDS: " <strong>{format!("{:.2}", total_ppvz_vw_nds)}</strong></span>
-                                <span>"Retail: " <strong>{format!("{:.2}", total_retail)}</strong></span>
-                                <span>"For Pay: " <strong>{format!("{:.2}", total_ppvz_for_pay)}</strong></span>
+                                <span>"PPVZ VW: " <strong class=amount_class(total_ppvz_vw)>{format_money(total_ppvz_vw)}</strong></span>
+                                <span>"PPVZ VW NDS: " <strong class=amount_class(total_ppvz_vw_nds)>{format_money(total_ppvz_vw_nds)}</strong></span>
+                                <span>"Retail: " <strong class=amount_class(total_retail)>{format_money(total_retail)}</strong></span>
+                                <span>"For Pay: " <strong class=amount_class(total_ppvz_for_pay)>{format_money(total_ppvz_for_pay)}</strong></span>
                             </Flex>
                         </CardAnimated>
                         <SalesDetailsCard vm=vm.clone() />
@@ -335,7 +329,7 @@ fn MarketplaceApiAmountsCard(vm: WbOrdersDetailsVm) -> impl IntoView {
                             />
                         </TableCellLayout></TableCell>
                         <TableCell><TableCellLayout><code>"salePrice"</code></TableCellLayout></TableCell>
-                        <TableCell attr:style="text-align: right;"><TableCellLayout>{format_money(sale_price)}</TableCellLayout></TableCell>
+                        <TableCell attr:style="text-align: right;"><TableCellLayout>{format_money_opt(sale_price)}</TableCellLayout></TableCell>
                         <TableCell><TableCellLayout>{unit_sale}</TableCellLayout></TableCell>
                     </TableRow>
                     <TableRow>
@@ -347,7 +341,7 @@ fn MarketplaceApiAmountsCard(vm: WbOrdersDetailsVm) -> impl IntoView {
                                     />
                                 </TableCellLayout></TableCell>
                                 <TableCell><TableCellLayout><code>"price"</code></TableCellLayout></TableCell>
-                                <TableCell attr:style="text-align: right;"><TableCellLayout>{format_money(price)}</TableCellLayout></TableCell>
+                                <TableCell attr:style="text-align: right;"><TableCellLayout>{format_money_opt(price)}</TableCellLayout></TableCell>
                                 <TableCell><TableCellLayout>{unit_price}</TableCellLayout></TableCell>
                             </TableRow>
                             <TableRow>
@@ -359,7 +353,7 @@ fn MarketplaceApiAmountsCard(vm: WbOrdersDetailsVm) -> impl IntoView {
                                     />
                                 </TableCellLayout></TableCell>
                                 <TableCell><TableCellLayout><code>"finalPrice"</code></TableCellLayout></TableCell>
-                                <TableCell attr:style="text-align: right;"><TableCellLayout>{format_money(final_price)}</TableCellLayout></TableCell>
+                                <TableCell attr:style="text-align: right;"><TableCellLayout>{format_money_opt(final_price)}</TableCellLayout></TableCell>
                                 <TableCell><TableCellLayout>{unit_final}</TableCellLayout></TableCell>
                             </TableRow>
                             <TableRow>
@@ -371,7 +365,7 @@ fn MarketplaceApiAmountsCard(vm: WbOrdersDetailsVm) -> impl IntoView {
                                     />
                                 </TableCellLayout></TableCell>
                                 <TableCell><TableCellLayout><code>"scanPrice"</code></TableCellLayout></TableCell>
-                                <TableCell attr:style="text-align: right;"><TableCellLayout>{format_money(scan_price)}</TableCellLayout></TableCell>
+                                <TableCell attr:style="text-align: right;"><TableCellLayout>{format_money_opt(scan_price)}</TableCellLayout></TableCell>
                                 <TableCell><TableCellLayout>{unit_scan}</TableCellLayout></TableCell>
                             </TableRow>
                         </TableBody>
@@ -3,6 +3,8 @@
 use super::super::view_model::WbOrdersDetailsVm;
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::components::card_animated::CardAnimated;
+use crate::shared::money_format::{amount_class, format_money, format_money_opt, format_percent_opt};
+use crate::system::print_templates::ui::PrintDocumentPanel;
 use contracts::projections::p903_wb_finance_report::dto::WbFinanceReportDto;
 use leptos::prelude::*;
 use thaw::*;
@@ -11,8 +13,10 @@ use thaw::*;
 pub fn LinksTab(vm: WbOrdersDetailsVm) -> impl IntoView {
     let tabs_store =
         leptos::context::use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
+    let document_id = vm.id;
 
     view! {
+        <PrintDocumentPanel entity_type="a015_wb_orders" document_id=document_id />
         {move || {
             if vm.finance_reports_loading.get() {
                 return view! {
@@ -50,12 +54,14 @@ pub fn LinksTab(vm: WbOrdersDetailsVm) -> impl IntoView {
                 .into_any();
             }
 
-            let reports_count = reports.len();
-            let total_ppvz_vw: f64 = reports.iter().filter_map(|r| r.ppvz_vw).sum();
-            let total_ppvz_vw_nds: f64 = reports.iter().filter_map(|r| r.ppvz_vw_nds).sum();
-            let total_retail: f64 = reports.iter().filter_map(|r| r.retail_amount).sum();
-            let total_ppvz_for_pay: f64 = reports.iter().filter_map(|r| r.ppvz_for_pay).sum();
-            let total_acquiring: f64 = reports.iter().filter_map(|r| r.acquiring_fee).sum();
+            // Totals are summed on the backend (p903 search-by-srid/totals)
+            let totals = vm.finance_reports_totals.get();
+            let reports_count = totals.count;
+            let total_ppvz_vw = totals.ppvz_vw;
+            let total_ppvz_vw_nds = totals.ppvz_vw_nds;
+            let total_retail = totals.retail_amount;
+            let total_ppvz_for_pay = totals.ppvz_for_pay;
+            let total_acquiring = totals.acquiring_fee;
             let reports_for_table = reports;
 
             view! {
@@ -66,11 +72,11 @@ pub fn LinksTab(vm: WbOrdersDetailsVm) -> impl IntoView {
                         <Badge appearance=BadgeAppearance::Tint color=BadgeColor::Brand>
                             {format!("Найдено: {}", reports_count)}
                         </Badge>
-                        <span>"PPVZ VW: " <strong>{format!("{:.2}", total_ppvz_vw)}</strong></span>
-                        <span>"PPVZ VW NDS: " <strong>{format!("{:.2}", total_ppvz_vw_nds)}</strong></span>
-                        <span>"Retail: " <strong>{format!("{:.2}", total_retail)}</strong></span>
-                        <span>"For Pay: " <strong>{format!("{:.2}", total_ppvz_for_pay)}</strong></span>
-                        <span>"Acquiring: " <strong>{format!("{:.2}", total_acquiring)}</strong></span>
+                        <span>"PPVZ VW: " <strong class=amount_class(total_ppvz_vw)>{format_money(total_ppvz_vw)}</strong></span>
+                        <span>"PPVZ VW NDS: " <strong class=amount_class(total_ppvz_vw_nds)>{format_money(total_ppvz_vw_nds)}</strong></span>
+                        <span>"Retail: " <strong class=amount_class(total_retail)>{format_money(total_retail)}</strong></span>
+                        <span>"For Pay: " <strong class=amount_class(total_ppvz_for_pay)>{format_money(total_ppvz_for_pay)}</strong></span>
+                        <span>"Acquiring: " <strong class=amount_class(total_acquiring)>{format_money(total_acquiring)}</strong></span>
                     </Flex>
 
                     <div style="max-height: calc(100vh - 400px); overflow: auto;">
@@ -83,7 +89,7 @@ pub fn LinksTab(vm: WbOrdersDetailsVm) -> impl IntoView {
                                     <TableHeaderCell>"PPVZ VW NDS"</TableHeaderCell>
                                     <TableHeaderCell>"Retail Amount"</TableHeaderCell>
                                     <TableHeaderCell>"PPVZ For Pay"</TableHeaderCell>
-                                    <TableHeaderCell>"Commission %"</TableHeaderCell>
+                                    <TableHeaderCell>"Commission"</TableHeaderCell>
                                     <TableHeaderCell>"Retail Price"</TableHeaderCell>
                                     <TableHeaderCell>"Retail w/Disc"</TableHeaderCell>
                                     <TableHeaderCell>"Acquiring Fee"</TableHeaderCell>
@@ -115,14 +121,14 @@ pub fn LinksTab(vm: WbOrdersDetailsVm) -> impl IntoView {
                                                 >
                                                     <TableCell><TableCellLayout>{rr_dt}</TableCellLayout></TableCell>
                                                     <TableCell><TableCellLayout>{rrd_id}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.ppvz_vw.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.ppvz_vw_nds.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.retail_amount.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.ppvz_for_pay.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.commission_percent.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.retail_price.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.retail_price_withdisc_rub.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{report.acquiring_fee.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "—".to_string())}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_money_opt(report.ppvz_vw)}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_money_opt(report.ppvz_vw_nds)}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_money_opt(report.retail_amount)}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_money_opt(report.ppvz_for_pay)}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_percent_opt(report.commission_percent, 2)}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_money_opt(report.retail_price)}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_money_opt(report.retail_price_withdisc_rub)}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_money_opt(report.acquiring_fee)}</TableCellLayout></TableCell>
                                                 </TableRow>
                                             }
                                         }
@@ -5,6 +5,7 @@ use super::super::view_model::WbOrdersDetailsVm;
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::components::card_animated::CardAnimated;
 use crate::shared::components::table::TableCellMoney;
+use crate::shared::money_format::{amount_class, format_money, format_qty};
 use leptos::prelude::*;
 use thaw::*;
 
@@ -73,8 +74,8 @@ pub fn SalesTab(vm: WbOrdersDetailsVm) -> impl IntoView {
                         <Badge appearance=BadgeAppearance::Tint color=BadgeColor::Brand>
                             {format!("Найдено: {}", sales_count)}
                         </Badge>
-                        <span>"Qty: " <strong>{format!("{:.2}", total_qty)}</strong></span>
-                        <span>"Сумма: " <strong>{format!("{:.2}", total_amount)}</strong></span>
+                        <span>"Qty: " <strong>{format_qty(total_qty)}</strong></span>
+                        <span>"Сумма: " <strong class=amount_class(total_amount)>{format_money(total_amount)}</strong></span>
                     </Flex>
 
                     <div style="max-height: calc(100vh - 400px); overflow: auto;">
@@ -125,7 +126,7 @@ pub fn SalesTab(vm: WbOrdersDetailsVm) -> impl IntoView {
                                                 >
                                                     <TableCell><TableCellLayout>{format_iso_date(&sale.state.sale_dt)}</TableCellLayout></TableCell>
                                                     <TableCell><TableCellLayout>{sale.line.supplier_article}</TableCellLayout></TableCell>
-                                                    <TableCell><TableCellLayout>{format!("{:.2}", sale.line.qty)}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout>{format_qty(sale.line.qty)}</TableCellLayout></TableCell>
                                                     <TableCellMoney value=sale.line.finished_price show_currency=false color_by_sign=false />
                                                     <TableCell>
                                                         <TableCellLayout>
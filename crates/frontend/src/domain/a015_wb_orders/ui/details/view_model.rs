@@ -1,7 +1,10 @@
 //! ViewModel for WB Orders details
 
 use super::model::*;
-use contracts::projections::p903_wb_finance_report::dto::WbFinanceReportDto;
+use contracts::domain::common::VersionConflict;
+use contracts::projections::p903_wb_finance_report::dto::{
+    WbFinanceReportDto, WbFinanceReportSridTotals,
+};
 use leptos::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
@@ -19,6 +22,7 @@ pub struct WbOrdersDetailsVm {
     pub marketplace_raw_json_loading: RwSignal<bool>,
 
     pub finance_reports: RwSignal<Vec<WbFinanceReportDto>>,
+    pub finance_reports_totals: RwSignal<WbFinanceReportSridTotals>,
     pub finance_reports_loaded: RwSignal<bool>,
     pub finance_reports_loading: RwSignal<bool>,
     pub finance_reports_error: RwSignal<Option<String>>,
@@ -46,6 +50,8 @@ pub struct WbOrdersDetailsVm {
     pub loading: RwSignal<bool>,
     pub posting: RwSignal<bool>,
     pub error: RwSignal<Option<String>>,
+    /// 409 на проведении: документ изменён после загрузки
+    pub version_conflict: RwSignal<Option<VersionConflict>>,
 }
 
 impl WbOrdersDetailsVm {
@@ -63,6 +69,7 @@ impl WbOrdersDetailsVm {
             marketplace_raw_json_loading: RwSignal::new(false),
 
             finance_reports: RwSignal::new(Vec::new()),
+            finance_reports_totals: RwSignal::new(WbFinanceReportSridTotals::default()),
             finance_reports_loaded: RwSignal::new(false),
             finance_reports_loading: RwSignal::new(false),
             finance_reports_error: RwSignal::new(None),
@@ -90,6 +97,7 @@ impl WbOrdersDetailsVm {
             loading: RwSignal::new(false),
             posting: RwSignal::new(false),
             error: RwSignal::new(None),
+            version_conflict: RwSignal::new(None),
         }
     }
 
@@ -98,6 +106,11 @@ impl WbOrdersDetailsVm {
         Signal::derive(move || order.get().map(|s| s.metadata.is_posted).unwrap_or(false))
     }
 
+    pub fn is_sandbox(&self) -> Signal<bool> {
+        let order = self.order;
+        Signal::derive(move || order.get().map(|s| s.is_sandbox).unwrap_or(false))
+    }
+
     pub fn document_no(&self) -> Signal<String> {
         let order = self.order;
         Signal::derive(move || {
@@ -321,9 +334,16 @@ impl WbOrdersDetailsVm {
         vm.finance_reports_error.set(None);
 
         spawn_local(async move {
-            match fetch_finance_reports(&srid).await {
-                Ok(reports) => {
+            let result = match fetch_finance_reports(&srid).await {
+                Ok(reports) => fetch_finance_reports_totals(&srid)
+                    .await
+                    .map(|totals| (reports, totals)),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok((reports, totals)) => {
                     vm.finance_reports.set(reports);
+                    vm.finance_reports_totals.set(totals);
                     vm.finance_reports_loaded.set(true);
                 }
                 Err(e) => vm.finance_reports_error.set(Some(e)),
@@ -388,19 +408,30 @@ impl WbOrdersDetailsVm {
         let Some(id) = self.id.get() else {
             return;
         };
+        let version = self
+            .order
+            .with_untracked(|o| o.as_ref().map(|o| o.metadata.version));
         let vm = self.clone();
         vm.posting.set(true);
 
         spawn_local(async move {
-            if let Err(e) = post_document(&id).await {
-                leptos::logging::log!("Error posting: {}", e);
-            } else {
-                vm.reload().await;
+            match post_document(&id, version).await {
+                Ok(()) => vm.reload().await,
+                Err(e) => match e.version_conflict() {
+                    Some(conflict) => vm.version_conflict.set(Some(conflict)),
+                    None => leptos::logging::log!("Error posting: {}", e),
+                },
             }
             vm.posting.set(false);
         });
     }
 
+    /// Перезагрузка документа после конфликта версий
+    pub fn reload_document(&self) {
+        let vm = self.clone();
+        spawn_local(async move { vm.reload().await });
+    }
+
     async fn reload(&self) {
         let Some(id) = self.id.get() else {
             return;
@@ -5,6 +5,10 @@ use crate::layout::global_context::AppGlobalContext;
 use crate::shared::change_tokens::ChangeTokenContext;
 use crate::shared::components::date_range_picker::DateRangePicker;
 use crate::shared::components::pagination_controls::PaginationControls;
+use crate::shared::components::quick_filter_input::QuickFilterInput;
+use crate::shared::components::row_context_menu::{
+    api_url, RawJsonSource, RowAction, RowContextMenu, RowMenuState, RowRef,
+};
 use crate::shared::components::table::{
     TableCellCheckbox, TableCellMoney, TableCrosshairHighlight, TableHeaderCheckbox,
 };
@@ -14,6 +18,7 @@ use crate::shared::list_utils::{get_sort_class, get_sort_indicator, Sortable};
 use crate::shared::page_frame::PageFrame;
 use crate::shared::table_utils::init_column_resize;
 use crate::system::tasks::ui::RunTaskButton;
+use contracts::domain::a015_wb_orders::quick_filter::QUICK_FILTER_FIELDS;
 use gloo_net::http::Request;
 use leptos::logging::log;
 use leptos::prelude::*;
@@ -23,12 +28,10 @@ use std::cmp::Ordering;
 use thaw::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures;
-use web_sys::{
-    Blob, BlobPropertyBag, HtmlAnchorElement, Request as WebRequest, RequestInit, RequestMode,
-    Response, Url,
-};
+use web_sys::{Request as WebRequest, RequestInit, RequestMode, Response};
 
 use crate::shared::api_utils::api_base;
+use crate::shared::auth_download::download_authenticated_file;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Organization {
@@ -83,8 +86,7 @@ fn currency_label(code: Option<i64>) -> &'static str {
     }
 }
 
-/// Разобрать один элемент ответа списка в DTO. Используется и при загрузке страницы,
-/// и при полной выгрузке в CSV.
+/// Разобрать один элемент ответа списка в DTO.
 fn parse_order_item(item: &serde_json::Value) -> Option<WbOrdersDto> {
     let id = item.get("id")?.as_str()?.to_string();
     let document_no = item.get("document_no")?.as_str()?.to_string();
@@ -259,9 +261,6 @@ pub fn WbOrdersList() -> impl IntoView {
     // Filter panel expansion state
     let (is_filter_expanded, set_is_filter_expanded) = signal(false);
 
-    // Show cancelled checkbox
-    let show_cancelled = RwSignal::new(state.get().show_cancelled);
-
     // Organizations
     let (organizations, set_organizations) = signal::<Vec<Organization>>(Vec::new());
 
@@ -301,13 +300,19 @@ pub fn WbOrdersList() -> impl IntoView {
             let page_size = state.with_untracked(|s| s.page_size);
             let sort_field = state.with_untracked(|s| s.sort_field.clone());
             let sort_ascending = state.with_untracked(|s| s.sort_ascending);
-            let show_cancelled_val = show_cancelled.get_untracked();
+            let (cancel_filter, posted_filter, warehouse) = state.with_untracked(|s| {
+                (
+                    s.cancel_filter.clone(),
+                    s.posted_filter.clone(),
+                    s.warehouse.clone(),
+                )
+            });
             let offset = page * page_size;
             let cache_buster = js_sys::Date::now() as i64;
 
             // Build URL with pagination parameters
             let mut url = format!(
-                "{}/api/a015/wb-orders?date_from={}&date_to={}&limit={}&offset={}&sort_by={}&sort_desc={}&show_cancelled={}&_ts={}",
+                "{}/api/a015/wb-orders?date_from={}&date_to={}&limit={}&offset={}&sort_by={}&sort_desc={}&_ts={}",
                 api_base(),
                 date_from_val,
                 date_to_val,
@@ -315,15 +320,22 @@ pub fn WbOrdersList() -> impl IntoView {
                 offset,
                 sort_field,
                 !sort_ascending,
-                show_cancelled_val,
                 cache_buster
             );
+            url.push_str(&status_filter_params(
+                &cancel_filter,
+                &posted_filter,
+                &warehouse,
+            ));
 
             if let Some(org_id) = org_id {
                 url.push_str(&format!("&organization_id={}", org_id));
             }
             if !search_query_val.is_empty() {
-                url.push_str(&format!("&search_query={}", search_query_val));
+                url.push_str(&format!(
+                    "&search_query={}",
+                    urlencoding::encode(&search_query_val)
+                ));
             }
 
             log!("Loading WB orders with URL: {}", url);
@@ -474,6 +486,39 @@ pub fn WbOrdersList() -> impl IntoView {
         });
     });
 
+    let cancel_filter = RwSignal::new(state.get_untracked().cancel_filter.clone());
+    Effect::new(move || {
+        let v = cancel_filter.get();
+        untrack(move || {
+            state.update(|s| {
+                s.cancel_filter = v;
+                s.page = 0;
+            });
+        });
+    });
+
+    let posted_filter = RwSignal::new(state.get_untracked().posted_filter.clone());
+    Effect::new(move || {
+        let v = posted_filter.get();
+        untrack(move || {
+            state.update(|s| {
+                s.posted_filter = v;
+                s.page = 0;
+            });
+        });
+    });
+
+    let warehouse = RwSignal::new(state.get_untracked().warehouse.clone());
+    Effect::new(move || {
+        let v = warehouse.get();
+        untrack(move || {
+            state.update(|s| {
+                s.warehouse = v;
+                s.page = 0;
+            });
+        });
+    });
+
     // Initialize column resize
     let resize_initialized = leptos::prelude::StoredValue::new(false);
     Effect::new(move |_| {
@@ -502,6 +547,15 @@ pub fn WbOrdersList() -> impl IntoView {
         if !s.search_query.is_empty() {
             count += 1;
         }
+        if !s.cancel_filter.is_empty() {
+            count += 1;
+        }
+        if !s.posted_filter.is_empty() {
+            count += 1;
+        }
+        if !s.warehouse.trim().is_empty() {
+            count += 1;
+        }
         count
     });
 
@@ -620,6 +674,35 @@ pub fn WbOrdersList() -> impl IntoView {
     let items_signal = Signal::derive(move || state.get().orders);
     let selected_signal = Signal::derive(move || selected.get());
 
+    // Контекстное меню строк (правый клик)
+    let row_menu = RowMenuState::new();
+    let row_actions = vec![
+        RowAction::new("Открыть", "eye", move |row: RowRef| {
+            open_detail(row.id, row.title)
+        }),
+        RowAction::post_to(
+            "Провести",
+            "check",
+            |id| api_url(&format!("/api/a015/wb-orders/{}/post", id)),
+            load_orders,
+        )
+        .write("a015_wb_orders"),
+        RowAction::post_to(
+            "Отменить проведение",
+            "x",
+            |id| api_url(&format!("/api/a015/wb-orders/{}/unpost", id)),
+            load_orders,
+        )
+        .write("a015_wb_orders"),
+        RowAction::copy_id(),
+        RowAction::raw_json(|id| RawJsonSource::ViaDocument {
+            document_url: api_url(&format!("/api/a015/wb-orders/{}", id)),
+            raw_url_prefix: api_url("/api/a015/raw"),
+        })
+        .read("a015_wb_orders"),
+        RowAction::favorite("a015_wb_orders_details", "a015_wb_orders_details_"),
+    ];
+
     view! {
         <PageFrame page_id="a015_wb_orders--list" category="list">
             <div class="page__header">
@@ -661,33 +744,22 @@ pub fn WbOrdersList() -> impl IntoView {
                             size=ButtonSize::Medium
                             on_click=move |_| {
                                 // Полная выгрузка по текущим фильтрам/сортировке, а не только
-                                // текущая страница: дёргаем сервер с большим лимитом.
-                                let date_from = state.with_untracked(|s| s.date_from.clone());
-                                let date_to = state.with_untracked(|s| s.date_to.clone());
-                                let org_id = state.with_untracked(|s| s.selected_organization_id.clone());
-                                let search_query_val = state.with_untracked(|s| s.search_query.clone());
-                                let sort_field = state.with_untracked(|s| s.sort_field.clone());
-                                let sort_ascending = state.with_untracked(|s| s.sort_ascending);
-                                let show_cancelled_val = show_cancelled.get_untracked();
+                                // текущая страница: сервер отдаёт CSV потоком.
+                                let url = state.with_untracked(|s| {
+                                    export_url(
+                                        &s.date_from,
+                                        &s.date_to,
+                                        s.selected_organization_id.as_deref(),
+                                        &s.search_query,
+                                        &s.sort_field,
+                                        s.sort_ascending,
+                                        &status_filter_params(&s.cancel_filter, &s.posted_filter, &s.warehouse),
+                                    )
+                                });
                                 set_exporting.set(true);
                                 spawn_local(async move {
-                                    match fetch_all_orders_for_export(
-                                        date_from,
-                                        date_to,
-                                        org_id,
-                                        search_query_val,
-                                        sort_field,
-                                        sort_ascending,
-                                        show_cancelled_val,
-                                    )
-                                    .await
-                                    {
-                                        Ok(data) => {
-                                            if let Err(e) = export_to_csv(&data) {
-                                                log!("Failed to export: {}", e);
-                                            }
-                                        }
-                                        Err(e) => log!("Failed to fetch full export: {}", e),
+                                    if let Err(e) = download_authenticated_file(&url, "wb_orders.csv").await {
+                                        log!("Failed to export: {}", e);
                                     }
                                     set_exporting.set(false);
                                 });
@@ -814,23 +886,43 @@ pub fn WbOrdersList() -> impl IntoView {
                                     </Flex>
                                 </div>
 
-                                <div style="flex: 1; max-width: 300px;">
+                                <div style="flex: 1; max-width: 420px;">
                                     <Flex vertical=true gap=FlexGap::Small>
                                         <Label>"Поиск:"</Label>
-                                        <Input
+                                        <QuickFilterInput
                                             value=search_query
-                                            placeholder="Артикул, номер, Line ID, наименование базы..."
+                                            fields=QUICK_FILTER_FIELDS
+                                            placeholder="Артикул, номер... или status:unposted amount>1000"
                                         />
                                     </Flex>
                                 </div>
 
                                 <div style="width: 180px;">
                                     <Flex vertical=true gap=FlexGap::Small>
-                                        <Label>" "</Label>
-                                        <Checkbox
-                                            checked=show_cancelled
-                                            label="Показать отменённые"
-                                        />
+                                        <Label>"Отмена:"</Label>
+                                        <Select value=cancel_filter>
+                                            <option value="">"Все"</option>
+                                            <option value="false">"Без отменённых"</option>
+                                            <option value="true">"Только отменённые"</option>
+                                        </Select>
+                                    </Flex>
+                                </div>
+
+                                <div style="width: 180px;">
+                                    <Flex vertical=true gap=FlexGap::Small>
+                                        <Label>"Проведение:"</Label>
+                                        <Select value=posted_filter>
+                                            <option value="">"Все"</option>
+                                            <option value="true">"Проведённые"</option>
+                                            <option value="false">"Непроведённые"</option>
+                                        </Select>
+                                    </Flex>
+                                </div>
+
+                                <div style="width: 200px;">
+                                    <Flex vertical=true gap=FlexGap::Small>
+                                        <Label>"Склад:"</Label>
+                                        <Input value=warehouse placeholder="Часть названия" />
                                     </Flex>
                                 </div>
 
@@ -984,9 +1076,10 @@ pub fn WbOrdersList() -> impl IntoView {
                                         .unwrap_or_else(|| order.document_no.clone());
                                     let formatted_date = format_date(&order.order_date);
                                     let formatted_time = format_time(&order.order_date);
+                                    let row_ref = RowRef::new(order.id.clone(), order.document_no.clone());
 
                                     view! {
-                                        <TableRow>
+                                        <TableRow on:contextmenu=move |ev| row_menu.open_at(&ev, row_ref.clone())>
                                             <TableCellCheckbox
                                                 item_id=order_id.clone()
                                                 selected=selected_signal
@@ -1156,58 +1249,58 @@ pub fn WbOrdersList() -> impl IntoView {
                     </Table>
                 </div>
             </div>
+            <RowContextMenu state=row_menu actions=row_actions />
         </PageFrame>
     }
 }
 
-/// Полная выгрузка заказов по текущим фильтрам/сортировке (все страницы) для CSV.
-async fn fetch_all_orders_for_export(
-    date_from: String,
-    date_to: String,
-    org_id: Option<String>,
-    search_query: String,
-    sort_field: String,
+/// Параметры серверных фильтров по отмене, проведению и складу (`&...`; пусто — без отбора).
+fn status_filter_params(cancel_filter: &str, posted_filter: &str, warehouse: &str) -> String {
+    let mut params = String::new();
+    if !cancel_filter.is_empty() {
+        params.push_str(&format!("&is_cancel={}", cancel_filter));
+    }
+    if !posted_filter.is_empty() {
+        params.push_str(&format!("&is_posted={}", posted_filter));
+    }
+    if !warehouse.trim().is_empty() {
+        params.push_str(&format!(
+            "&warehouse={}",
+            urlencoding::encode(warehouse.trim())
+        ));
+    }
+    params
+}
+
+/// URL серверной CSV-выгрузки заказов по текущим фильтрам/сортировке (все страницы).
+fn export_url(
+    date_from: &str,
+    date_to: &str,
+    org_id: Option<&str>,
+    search_query: &str,
+    sort_field: &str,
     sort_ascending: bool,
-    show_cancelled: bool,
-) -> Result<Vec<WbOrdersDto>, String> {
-    let cache_buster = js_sys::Date::now() as i64;
+    status_params: &str,
+) -> String {
     let mut url = format!(
-        "{}/api/a015/wb-orders?date_from={}&date_to={}&limit={}&offset=0&sort_by={}&sort_desc={}&show_cancelled={}&_ts={}",
+        "{}/api/a015/wb-orders/export?format=csv&date_from={}&date_to={}&sort_by={}&sort_desc={}{}",
         api_base(),
         date_from,
         date_to,
-        10_000_000,
         sort_field,
         !sort_ascending,
-        show_cancelled,
-        cache_buster
+        status_params
     );
-    if let Some(org_id) = org_id {
-        if !org_id.is_empty() {
-            url.push_str(&format!("&organization_id={}", org_id));
-        }
+    if let Some(org_id) = org_id.filter(|id| !id.is_empty()) {
+        url.push_str(&format!("&organization_id={}", org_id));
     }
     if !search_query.is_empty() {
-        url.push_str(&format!("&search_query={}", search_query));
-    }
-
-    let response = Request::get(&url)
-        .header("Cache-Control", "no-cache, no-store, must-revalidate")
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch: {}", e))?;
-    if !response.ok() {
-        return Err(format!("Server error: {}", response.status()));
+        url.push_str(&format!(
+            "&search_query={}",
+            urlencoding::encode(search_query)
+        ));
     }
-    let paginated = response
-        .json::<PaginatedResponse>()
-        .await
-        .map_err(|e| format!("Failed to parse: {}", e))?;
-    Ok(paginated
-        .items
-        .iter()
-        .filter_map(parse_order_item)
-        .collect())
+    url
 }
 
 /// Загрузка списка организаций
@@ -1266,110 +1359,3 @@ async fn load_saved_settings(form_key: &str) -> Result<Option<serde_json::Value>
         }
     }
 }
-
-/// Экспорт WB Orders в CSV для Excel
-fn export_to_csv(data: &[WbOrdersDto]) -> Result<(), String> {
-    let mut csv = String::from("\u{FEFF}");
-
-    csv.push_str("Номер заказа;Дата заказа;Организация;Артикул продавца;Артикул МП;Артикул 1С;Артикул база;Наименование база;Бренд;Маржа, %;Дил. цена УТ;Итоговая цена;Валюта;Отменён\n");
-
-    for order in data {
-        let order_date = format_date(&order.order_date);
-        let org_name = order
-            .organization_name
-            .as_ref()
-            .map(|s| s.as_str())
-            .unwrap_or("—");
-        let mp_article = order
-            .marketplace_article
-            .as_ref()
-            .map(|s| s.as_str())
-            .unwrap_or("—");
-        let base_nom_article = order
-            .base_nomenclature_article
-            .as_ref()
-            .map(|s| s.as_str())
-            .unwrap_or("—");
-        let base_nom_description = order
-            .base_nomenclature_description
-            .as_ref()
-            .map(|s| s.as_str())
-            .unwrap_or("—");
-        let nom_article = order
-            .nomenclature_article
-            .as_ref()
-            .map(|s| s.as_str())
-            .unwrap_or("—");
-        let brand = order.brand.as_ref().map(|s| s.as_str()).unwrap_or("—");
-
-        let margin_str = order
-            .margin_pro
-            .map(|a| format!("{:.2}", a).replace(".", ","))
-            .unwrap_or_else(|| "—".to_string());
-        let dealer_price_str = order
-            .dealer_price_ut
-            .map(|a| format!("{:.2}", a).replace(".", ","))
-            .unwrap_or_else(|| "—".to_string());
-        let finished_price_str = order
-            .finished_price
-            .map(|a| format!("{:.2}", a).replace(".", ","))
-            .unwrap_or_else(|| "—".to_string());
-        let is_cancel_str = if order.is_cancel { "Да" } else { "Нет" };
-        // Пусто для RUB → показываем явно "RUB", иначе код валюты (KZT/BYN/AMD).
-        let currency_str = match currency_label(order.currency_code) {
-            "" => "RUB",
-            label => label,
-        };
-
-        csv.push_str(&format!(
-            "\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";{};{};{};\"{}\";\"{}\"\n",
-            order.document_no.replace('\"', "\"\""),
-            order_date,
-            org_name.replace('\"', "\"\""),
-            order.supplier_article.replace('\"', "\"\""),
-            mp_article.replace('\"', "\"\""),
-            nom_article.replace('\"', "\"\""),
-            base_nom_article.replace('\"', "\"\""),
-            base_nom_description.replace('\"', "\"\""),
-            brand.replace('\"', "\"\""),
-            margin_str,
-            dealer_price_str,
-            finished_price_str,
-            currency_str,
-            is_cancel_str
-        ));
-    }
-
-    let blob_parts = js_sys::Array::new();
-    blob_parts.push(&wasm_bindgen::JsValue::from_str(&csv));
-
-    let blob_props = BlobPropertyBag::new();
-    blob_props.set_type("text/csv;charset=utf-8;");
-
-    let blob = Blob::new_with_str_sequence_and_options(&blob_parts, &blob_props)
-        .map_err(|e| format!("Failed to create blob: {:?}", e))?;
-
-    let url = Url::create_object_url_with_blob(&blob)
-        .map_err(|e| format!("Failed to create URL: {:?}", e))?;
-
-    let window = web_sys::window().ok_or_else(|| "no window".to_string())?;
-    let document = window.document().ok_or_else(|| "no document".to_string())?;
-
-    let a = document
-        .create_element("a")
-        .map_err(|e| format!("Failed to create element: {:?}", e))?
-        .dyn_into::<HtmlAnchorElement>()
-        .map_err(|e| format!("Failed to cast to anchor: {:?}", e))?;
-
-    a.set_href(&url);
-    let filename = format!(
-        "wb_orders_{}.csv",
-        chrono::Utc::now().format("%Y%m%d_%H%M%S")
-    );
-    a.set_download(&filename);
-    a.click();
-
-    Url::revoke_object_url(&url).map_err(|e| format!("Failed to revoke URL: {:?}", e))?;
-
-    Ok(())
-}
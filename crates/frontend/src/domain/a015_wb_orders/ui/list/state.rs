@@ -16,7 +16,12 @@ pub struct WbOrdersState {
     // Search fields
     pub search_query: String,
     // Filter fields
-    pub show_cancelled: bool,
+    /// "" — все, "true" — только отменённые, "false" — без отменённых
+    pub cancel_filter: String,
+    /// "" — все, "true" — проведённые, "false" — непроведённые
+    pub posted_filter: String,
+    /// Часть названия склада
+    pub warehouse: String,
     // Pagination fields
     pub page: usize,
     pub page_size: usize,
@@ -53,7 +58,9 @@ impl Default for WbOrdersState {
             is_loaded: false,
             search_query: String::new(),
             // Filter defaults
-            show_cancelled: true, // Show all by default
+            cancel_filter: String::new(), // Show all by default
+            posted_filter: String::new(),
+            warehouse: String::new(),
             // Pagination defaults
             page: 0,
             page_size: 100,
@@ -17,6 +17,8 @@ pub struct YmReturnDetailDto {
     pub state: StateDto,
     pub source_meta: SourceMetaDto,
     pub is_posted: bool,
+    #[serde(default)]
+    pub is_sandbox: bool,
 }
 
 /// Return header information
@@ -3,9 +3,13 @@
 use super::tabs::{GeneralTab, JsonTab, LinesTab, ProjectionsTab};
 use super::view_model::YmReturnDetailsVm;
 use crate::layout::global_context::AppGlobalContext;
+use crate::shared::components::sandbox_badge::SandboxBadge;
 use crate::shared::icons::icon;
 use crate::shared::page_frame::PageFrame;
+use crate::system::document_audit::ui::DocumentHistoryPanel;
 use crate::system::favorites::ui::FavoriteButton;
+use crate::system::return_dispositions::ui::ReturnDispositionPanel;
+use crate::system::scheduled_posts::ui::ScheduledPostControl;
 use leptos::prelude::*;
 use thaw::*;
 
@@ -80,6 +84,7 @@ pub fn YmReturnDetail(id: String, #[prop(into)] on_close: Callback<()>) -> impl
                         view! { <div>"Нет данных"</div> }.into_any()
                     }
                 }}
+                <ReturnDispositionPanel entity_type="a016_ym_returns" document_id=stored_id.get_value() />
             </div>
         </PageFrame>
     }
@@ -92,6 +97,7 @@ fn Header(
     on_close: Callback<()>,
 ) -> impl IntoView {
     let is_posted = vm.is_posted();
+    let is_sandbox = vm.is_sandbox();
     let title = vm.title();
     let return_data = vm.return_data;
     let tab_key = format!("a016_ym_returns_details_{}", favorite_target_id);
@@ -119,8 +125,10 @@ fn Header(
                         }
                     }}
                 </Show>
+                <SandboxBadge is_sandbox=is_sandbox />
             </div>
             <div class="page__header-right">
+                <ScheduledPostControl entity_type="a016_ym_returns" document_id=vm.id />
                 <PostButtons vm=vm.clone() />
 
                 <Button
@@ -248,10 +256,21 @@ fn TabBar(vm: YmReturnDetailsVm) -> impl IntoView {
             <button
                 class="page__tab"
                 class:page__tab--active=move || active_tab.get() == "json"
-                on:click=move |_| vm.set_tab("json")
+                on:click={
+                    let vm = vm.clone();
+                    move |_| vm.set_tab("json")
+                }
             >
                 {icon("code")} "JSON"
             </button>
+
+            <button
+                class="page__tab"
+                class:page__tab--active=move || active_tab.get() == "history"
+                on:click=move |_| vm.set_tab("history")
+            >
+                {icon("clock")} "История"
+            </button>
         </div>
     }
 }
@@ -271,6 +290,7 @@ fn TabContent(vm: YmReturnDetailsVm) -> impl IntoView {
     let projections = vm.projections;
     let projections_loading = vm.projections_loading;
     let raw_json = vm.raw_json;
+    let document_id = vm.id;
 
     view! {
         {move || match active_tab.get() {
@@ -300,6 +320,7 @@ fn TabContent(vm: YmReturnDetailsVm) -> impl IntoView {
             }
             .into_any(),
             "json" => view! { <JsonTab raw_json=raw_json.into() /> }.into_any(),
+            "history" => view! { <DocumentHistoryPanel entity_type="a016_ym_returns" document_id=document_id /> }.into_any(),
             _ => view! { <GeneralTab vm=vm_general.clone() /> }.into_any(),
         }}
     }
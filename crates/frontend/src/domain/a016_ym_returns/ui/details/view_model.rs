@@ -60,6 +60,11 @@ impl YmReturnDetailsVm {
         Signal::derive(move || data.get().map(|d| d.is_posted).unwrap_or(false))
     }
 
+    pub fn is_sandbox(&self) -> Signal<bool> {
+        let data = self.return_data;
+        Signal::derive(move || data.get().map(|d| d.is_sandbox).unwrap_or(false))
+    }
+
     /// Заголовок документа: «YM Возврат {return_id}»
     pub fn title(&self) -> Signal<String> {
         let data = self.return_data;
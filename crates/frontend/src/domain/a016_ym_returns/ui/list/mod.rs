@@ -14,12 +14,14 @@ use crate::shared::icons::icon;
 use crate::shared::list_utils::{format_number, get_sort_class, get_sort_indicator, Sortable};
 use crate::shared::page_frame::PageFrame;
 use crate::shared::table_utils::{init_column_resize, was_just_resizing};
+use crate::system::auth::context::{can_view_finance, use_auth};
+use crate::system::operations::api::run_operation;
+use contracts::system::operations::OperationRequest;
 use gloo_net::http::Request;
 use leptos::logging::log;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use thaw::*;
@@ -99,6 +101,9 @@ pub fn YmReturnsList() -> impl IntoView {
     let (error, set_error) = signal::<Option<String>>(None);
     let (posting_in_progress, set_posting_in_progress) = signal(false);
     let (is_filter_expanded, set_is_filter_expanded) = signal(false);
+    // Суммы скрыты для ролей без доступа к финансам.
+    let (auth_state, _) = use_auth();
+    let show_finance = can_view_finance(auth_state);
 
     let search_return_id = RwSignal::new(state.get_untracked().search_return_id.clone());
     let search_order_id = RwSignal::new(state.get_untracked().search_order_id.clone());
@@ -363,21 +368,16 @@ pub fn YmReturnsList() -> impl IntoView {
         }
         set_posting_in_progress.set(true);
         spawn_local(async move {
-            let body = json!({ "ids": ids });
-            match Request::post(&format!("{}/api/a016/ym-returns/batch-post", api_base()))
-                .header("Content-Type", "application/json")
-                .body(body.to_string())
-                .unwrap()
-                .send()
-                .await
-            {
-                Ok(resp) => {
-                    if resp.status() == 200 {
-                        state.update(|s| s.selected_ids.clear());
-                        load_data();
-                    }
+            let request = OperationRequest::PostDocuments {
+                entity_type: "a016_ym_returns".to_string(),
+                ids,
+            };
+            match run_operation(request, |_, _| {}).await {
+                Ok(_) => {
+                    state.update(|s| s.selected_ids.clear());
+                    load_data();
                 }
-                Err(e) => log!("Batch post error: {:?}", e),
+                Err(e) => log!("Batch post error: {}", e),
             }
             set_posting_in_progress.set(false);
         });
@@ -390,21 +390,16 @@ pub fn YmReturnsList() -> impl IntoView {
         }
         set_posting_in_progress.set(true);
         spawn_local(async move {
-            let body = json!({ "ids": ids });
-            match Request::post(&format!("{}/api/a016/ym-returns/batch-unpost", api_base()))
-                .header("Content-Type", "application/json")
-                .body(body.to_string())
-                .unwrap()
-                .send()
-                .await
-            {
-                Ok(resp) => {
-                    if resp.status() == 200 {
-                        state.update(|s| s.selected_ids.clear());
-                        load_data();
-                    }
+            let request = OperationRequest::UnpostDocuments {
+                entity_type: "a016_ym_returns".to_string(),
+                ids,
+            };
+            match run_operation(request, |_, _| {}).await {
+                Ok(_) => {
+                    state.update(|s| s.selected_ids.clear());
+                    load_data();
                 }
-                Err(e) => log!("Batch unpost error: {:?}", e),
+                Err(e) => log!("Batch unpost error: {}", e),
             }
             set_posting_in_progress.set(false);
         });
@@ -413,16 +408,25 @@ pub fn YmReturnsList() -> impl IntoView {
     let export_excel = move |_| {
         let data = items.get();
         let mut csv = String::from("\u{FEFF}");
-        csv.push_str("Return ID;Order ID;Тип;Статус;Кол-во;Сумма;Дата;Проведен\n");
+        csv.push_str(if show_finance {
+            "Return ID;Order ID;Тип;Статус;Кол-во;Сумма;Дата;Проведен\n"
+        } else {
+            "Return ID;Order ID;Тип;Статус;Кол-во;Дата;Проведен\n"
+        });
         for item in data.iter() {
+            let amount = if show_finance {
+                format!("{};", format_number(item.total_amount))
+            } else {
+                String::new()
+            };
             csv.push_str(&format!(
-                "{};{};{};{};{};{};{};{}\n",
+                "{};{};{};{};{};{}{};{}\n",
                 item.return_id,
                 item.order_id,
                 item.return_type,
                 item.refund_status,
                 item.total_items,
-                format_number(item.total_amount),
+                amount,
                 format_datetime_utc_local(&item.created_at_source, "%d.%m.%Y %H:%M:%S"),
                 if item.is_posted { "Да" } else { "Нет" }
             ));
@@ -669,8 +673,10 @@ pub fn YmReturnsList() -> impl IntoView {
                         <span>"Записей: " {totals.total_records} " | "</span>
                         <span>"Возвратов: " {totals.returns_count} " | "</span>
                         <span>"Невыкупов: " {totals.unredeemed_count} " | "</span>
-                        <span>"Товаров: " {totals.sum_items} " | "</span>
-                        <span>"Сумма: " {format_number(totals.sum_amount)}</span>
+                        <span>"Товаров: " {totals.sum_items}</span>
+                        {show_finance.then(|| view! {
+                            <span>" | Сумма: " {format_number(totals.sum_amount)}</span>
+                        })}
                     </div>
                 })}
 
@@ -751,14 +757,16 @@ pub fn YmReturnsList() -> impl IntoView {
                                     </div>
                                 </TableHeaderCell>
 
-                                <TableHeaderCell resizable=false min_width=55.0 class="resizable">
-                                    <div class="table__sortable-header" style="cursor: pointer;" on:click=toggle_sort("total_amount")>
-                                        "Сумма"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "total_amount"))>
-                                            {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "total_amount", state.with(|s| s.sort_ascending))}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
+                                {show_finance.then(|| view! {
+                                    <TableHeaderCell resizable=false min_width=55.0 class="resizable">
+                                        <div class="table__sortable-header" style="cursor: pointer;" on:click=toggle_sort("total_amount")>
+                                            "Сумма"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "total_amount"))>
+                                                {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "total_amount", state.with(|s| s.sort_ascending))}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+                                })}
                             </TableRow>
                         </TableHeader>
 
@@ -850,11 +858,13 @@ pub fn YmReturnsList() -> impl IntoView {
                                                     <span style="font-variant-numeric: tabular-nums;">{item.total_items}</span>
                                                 </TableCellLayout>
                                             </TableCell>
-                                            <TableCellMoney
-                                                value=Signal::derive(move || Some(item.total_amount))
-                                                show_currency=false
-                                                color_by_sign=false
-                                            />
+                                            {show_finance.then(|| view! {
+                                                <TableCellMoney
+                                                    value=Signal::derive(move || Some(item.total_amount))
+                                                    show_currency=false
+                                                    color_by_sign=false
+                                                />
+                                            })}
                                         </TableRow>
                                     }
                                 }
@@ -3,6 +3,7 @@ use super::view_model::WbPromotionDetailsVm;
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::icons::icon;
 use crate::shared::page_frame::PageFrame;
+use crate::system::document_audit::ui::DocumentHistoryPanel;
 use leptos::prelude::*;
 use thaw::*;
 
@@ -164,6 +165,18 @@ fn TabBar(vm: WbPromotionDetailsVm) -> impl IntoView {
                 {tab_icon("code")}
                 "JSON"
             </Button>
+
+            <Button
+                appearance=Signal::derive({
+                    let active_tab = active_tab;
+                    move || if active_tab.get() == "history" { ButtonAppearance::Primary } else { ButtonAppearance::Subtle }
+                })
+                size=ButtonSize::Small
+                on_click=move |_| vm.set_tab("history")
+            >
+                {tab_icon("clock")}
+                "История"
+            </Button>
         </Flex>
     }
 }
@@ -174,12 +187,14 @@ fn TabContent(vm: WbPromotionDetailsVm) -> impl IntoView {
     let vm_general = vm.clone();
     let vm_nomenclatures = vm.clone();
     let vm_json = vm.clone();
+    let document_id = vm.id;
 
     view! {
         {move || match active_tab.get() {
             "general" => view! { <GeneralTab vm=vm_general.clone() /> }.into_any(),
             "nomenclatures" => view! { <NomenclaturesTab vm=vm_nomenclatures.clone() /> }.into_any(),
             "json" => view! { <JsonTab vm=vm_json.clone() /> }.into_any(),
+            "history" => view! { <DocumentHistoryPanel entity_type="a020_wb_promotion" document_id=document_id /> }.into_any(),
             _ => view! { <GeneralTab vm=vm_general.clone() /> }.into_any(),
         }}
     }
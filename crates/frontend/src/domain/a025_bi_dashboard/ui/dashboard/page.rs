@@ -2458,6 +2458,40 @@ fn DashboardConnectionMpControls(ctx: RwSignal<ViewContext>) -> impl IntoView {
     }
 }
 
+/// Чип активного кросс-фильтра в панели фильтров; крестик снимает срез.
+#[component]
+fn DashboardCrossFilterChip(cross_filter: RwSignal<Option<DashboardCrossFilter>>) -> impl IntoView {
+    move || {
+        let Some(current) = cross_filter.get() else {
+            return view! { <></> }.into_any();
+        };
+        let title = format!("Срез выбран в индикаторе «{}»", current.source_name);
+        view! {
+            <div class="dashboard-mp-controls">
+                <span class="dmc-section-label">"Кросс-фильтр"</span>
+                <div class="dmc-group">
+                    <span class="dmc-btn dmc-btn--active" title=title>
+                        {format!(
+                            "{}: {}",
+                            cross_filter_dimension_label(&current.dimension_id),
+                            current.label
+                        )}
+                    </span>
+                    <button
+                        type="button"
+                        class="dmc-btn"
+                        title="Снять кросс-фильтр"
+                        on:click=move |_| cross_filter.set(None)
+                    >
+                        {icon("x")}
+                    </button>
+                </div>
+            </div>
+        }
+        .into_any()
+    }
+}
+
 /// State passed from the iframe postMessage to drive the detail modal.
 #[derive(Clone, Debug)]
 struct IndicatorSelection {
@@ -2468,6 +2502,54 @@ struct IndicatorSelection {
     from_y: f64,
 }
 
+/// Кросс-фильтр дашборда: клик по столбцу среза в карточке одного индикатора
+/// сужает общий `ViewContext` для всех карточек страницы.
+#[derive(Clone, Debug, PartialEq)]
+struct DashboardCrossFilter {
+    /// Измерение DataView, по которому выбран срез (см. [`CROSS_FILTER_DIMENSIONS`])
+    dimension_id: String,
+    /// `group_key` строки drilldown
+    value: String,
+    /// Человекочитаемое значение для чипа в панели фильтров
+    label: String,
+    /// Название индикатора, из которого выбран срез
+    source_name: String,
+}
+
+/// Измерения, срез по которым можно применить ко всему дашборду.
+/// Кабинет МП уходит в `connection_mp_refs`, номенклатура — в параметр
+/// `nomenclature_ref`: его понимают DataView с этим измерением, остальные
+/// карточки при таком срезе не считаются.
+const CROSS_FILTER_DIMENSIONS: &[(&str, &str)] = &[
+    ("connection_mp_ref", "Кабинет МП"),
+    ("nomenclature_ref", "Номенклатура"),
+];
+
+fn cross_filter_dimension_label(dimension_id: &str) -> &'static str {
+    CROSS_FILTER_DIMENSIONS
+        .iter()
+        .find(|(id, _)| *id == dimension_id)
+        .map(|(_, label)| *label)
+        .unwrap_or("Срез")
+}
+
+/// Контекст, с которым считаются карточки: фильтры панели + активный кросс-фильтр.
+fn apply_cross_filter(ctx: &ViewContext, cross: Option<&DashboardCrossFilter>) -> ViewContext {
+    let mut effective = ctx.clone();
+    if let Some(cross) = cross {
+        match cross.dimension_id.as_str() {
+            "connection_mp_ref" => effective.connection_mp_refs = vec![cross.value.clone()],
+            "nomenclature_ref" => {
+                effective
+                    .params
+                    .insert("nomenclature_ref".to_string(), cross.value.clone());
+            }
+            _ => {}
+        }
+    }
+    effective
+}
+
 #[component]
 pub fn BiDashboardView(id: String) -> impl IntoView {
     let tabs_ctx = use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
@@ -2484,6 +2566,7 @@ pub fn BiDashboardView(id: String) -> impl IntoView {
     let dashboard_design: RwSignal<String> = RwSignal::new(default_design_name().to_string());
     let thaw_theme_ctx = leptos::context::use_context::<ThawThemeContext>();
     let selected_indicator: RwSignal<Option<IndicatorSelection>> = RwSignal::new(None);
+    let cross_filter: RwSignal<Option<DashboardCrossFilter>> = RwSignal::new(None);
 
     // Listen for postMessage events from the indicator cards iframe.
     // The handle must be stored until cleanup — WindowListenerHandle has no Drop impl,
@@ -2563,6 +2646,7 @@ pub fn BiDashboardView(id: String) -> impl IntoView {
     let active_filters_count = Signal::derive(move || {
         visible_dashboard_filters.get().len()
             + usize::from(!view_ctx.get().connection_mp_refs.is_empty())
+            + usize::from(cross_filter.get().is_some())
     });
 
     reload_dashboard_data(
@@ -2579,7 +2663,7 @@ pub fn BiDashboardView(id: String) -> impl IntoView {
 
     // Реактивный эффект: пересчитываем данные индикаторов при смене фильтров
     Effect::new(move |_| {
-        let ctx = view_ctx.get();
+        let ctx = apply_cross_filter(&view_ctx.get(), cross_filter.get().as_ref());
         let defs = indicator_defs.get();
         let request_id = indicator_refresh_seq.get_untracked().wrapping_add(1);
         indicator_refresh_seq.set(request_id);
@@ -2750,6 +2834,7 @@ pub fn BiDashboardView(id: String) -> impl IntoView {
                             <div class="filter-panel-content">
                                 <DashboardPeriodControls ctx=view_ctx />
                                 <DashboardConnectionMpControls ctx=view_ctx />
+                                <DashboardCrossFilterChip cross_filter=cross_filter />
                                 {move || {
                                     let filters = visible_dashboard_filters.get();
                                     if filters.is_empty() {
@@ -2793,12 +2878,15 @@ pub fn BiDashboardView(id: String) -> impl IntoView {
                     return view! { <></> }.into_any();
                 };
                 let on_close = Callback::new(move |_| selected_indicator.set(None));
+                let on_cross_filter =
+                    Callback::new(move |next: DashboardCrossFilter| cross_filter.set(Some(next)));
                 view! {
                     <IndicatorDetailModal
                         sel=sel
                         indicator_defs=indicator_defs
                         indicator_values=indicator_values
                         on_close=on_close
+                        on_cross_filter=on_cross_filter
                         ctx=rendered_ctx.get_untracked()
                     />
                 }.into_any()
@@ -2834,6 +2922,7 @@ fn IndicatorDetailModal(
     indicator_defs: RwSignal<HashMap<String, IndicatorDef>>,
     indicator_values: RwSignal<HashMap<String, ComputedValue>>,
     on_close: Callback<()>,
+    on_cross_filter: Callback<DashboardCrossFilter>,
     ctx: ViewContext,
 ) -> impl IntoView {
     let def = indicator_defs.get_untracked().get(&sel.id).cloned();
@@ -3248,6 +3337,11 @@ fn IndicatorDetailModal(
                         let metric_id_c = metric_id_opt.clone();
                         let ctx_c = ctx.clone();
                         let tabs_store = Some(tabs_store.clone());
+                        let slice_view_id = view_id_c.clone();
+                        let slice_metric_id = metric_id_c.clone();
+                        let slice_ctx = ctx.clone();
+                        let slice_source_name = name.clone();
+                        let slice_format_spec = format_spec.clone();
 
                         view! {
                             <>
@@ -3396,6 +3490,35 @@ fn IndicatorDetailModal(
                                     }
                                 }}
                             </div>
+                            {move || {
+                                let cross_dimensions: Vec<DrillDim> = dv_dims
+                                    .get()
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .filter(|dim| {
+                                        dim.mode == "safe"
+                                            && CROSS_FILTER_DIMENSIONS.iter().any(|(id, _)| *id == dim.id)
+                                    })
+                                    .collect();
+                                cross_dimensions
+                                    .into_iter()
+                                    .map(|dim| view! {
+                                        <CrossFilterSlice
+                                            view_id=slice_view_id.clone()
+                                            metric_id=slice_metric_id.clone()
+                                            dimension_id=dim.id
+                                            ctx=slice_ctx.clone()
+                                            params=overview_effective_indicator_params.get_value()
+                                            source_name=slice_source_name.clone()
+                                            format_spec=slice_format_spec.clone()
+                                            on_select=Callback::new(move |next: DashboardCrossFilter| {
+                                                on_cross_filter.run(next);
+                                                do_close.run(());
+                                            })
+                                        />
+                                    })
+                                    .collect_view()
+                            }}
                             </>
                         }.into_any()
                     }}
@@ -3405,6 +3528,105 @@ fn IndicatorDetailModal(
     }
 }
 
+/// Горизонтальный bar-срез индикатора по измерению кросс-фильтра.
+/// Клик по столбцу применяет значение ко всем карточкам дашборда.
+#[component]
+fn CrossFilterSlice(
+    view_id: String,
+    metric_id: Option<String>,
+    dimension_id: String,
+    ctx: ViewContext,
+    params: HashMap<String, String>,
+    source_name: String,
+    format_spec: serde_json::Value,
+    on_select: Callback<DashboardCrossFilter>,
+) -> impl IntoView {
+    let rows: RwSignal<Option<Result<Vec<(String, String, f64)>, String>>> = RwSignal::new(None);
+
+    {
+        let dimension_id = dimension_id.clone();
+        let mut slice_ctx = ctx;
+        slice_ctx.params = params;
+        spawn_local(async move {
+            let result = dv_api::fetch_drilldown(&view_id, &slice_ctx, &dimension_id, metric_id)
+                .await
+                .map(|resp| {
+                    let mut items: Vec<(String, String, f64)> = resp
+                        .rows
+                        .into_iter()
+                        .filter(|row| !row.group_key.trim().is_empty())
+                        .map(|row| (row.group_key, row.label, row.value1))
+                        .collect();
+                    items.sort_by(|a, b| b.2.abs().total_cmp(&a.2.abs()));
+                    items
+                });
+            rows.set(Some(result));
+        });
+    }
+
+    let dimension_label = cross_filter_dimension_label(&dimension_id);
+
+    view! {
+        <section class="indicator-detail__section cross-filter-slice">
+            <span class="indicator-detail__section-eyebrow">
+                {format!("Срез по измерению «{}» — клик фильтрует дашборд", dimension_label)}
+            </span>
+            {move || match rows.get() {
+                None => view! {
+                    <div class="drill-picker__loading">
+                        <span class="spinner spinner--sm" />
+                        " Загрузка среза..."
+                    </div>
+                }.into_any(),
+                Some(Err(err)) => view! {
+                    <div class="indicator-detail__empty">{format!("Срез недоступен: {}", err)}</div>
+                }.into_any(),
+                Some(Ok(items)) if items.is_empty() => view! {
+                    <div class="indicator-detail__empty">"Нет данных для среза за текущий период."</div>
+                }.into_any(),
+                Some(Ok(items)) => {
+                    let max_abs = items
+                        .iter()
+                        .map(|(_, _, value)| value.abs())
+                        .fold(0.0_f64, f64::max);
+                    items
+                        .into_iter()
+                        .map(|(key, label, value)| {
+                            let width_pct = if max_abs > 0.0 { value.abs() / max_abs * 100.0 } else { 0.0 };
+                            let bar_label = label.clone();
+                            let next = DashboardCrossFilter {
+                                dimension_id: dimension_id.clone(),
+                                value: key,
+                                label,
+                                source_name: source_name.clone(),
+                            };
+                            view! {
+                                <button
+                                    type="button"
+                                    class="cross-filter-slice__row"
+                                    on:click=move |_| on_select.run(next.clone())
+                                >
+                                    <span class="cross-filter-slice__label">{bar_label}</span>
+                                    <span class="cross-filter-slice__track">
+                                        <span
+                                            class="cross-filter-slice__bar"
+                                            style=format!("width: {:.1}%;", width_pct)
+                                        />
+                                    </span>
+                                    <span class="cross-filter-slice__value">
+                                        {format_value(value, &format_spec)}
+                                    </span>
+                                </button>
+                            }
+                        })
+                        .collect_view()
+                        .into_any()
+                }
+            }}
+        </section>
+    }
+}
+
 // ── Drilldown session helper ──────────────────────────────────────────────────
 
 async fn post_drilldown_session(
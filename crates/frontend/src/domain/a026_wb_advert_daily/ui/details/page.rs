@@ -10,6 +10,7 @@ use crate::shared::components::more_actions_menu::{use_more_actions_close, MoreA
 use crate::shared::icons::icon;
 use crate::shared::page_frame::PageFrame;
 use crate::shared::page_standard::PAGE_CAT_DETAIL;
+use crate::system::document_audit::ui::DocumentHistoryPanel;
 use crate::system::favorites::ui::FavoriteButton;
 use leptos::prelude::*;
 use thaw::*;
@@ -273,6 +274,14 @@ fn TabBar(vm: WbAdvertDailyDetailsVm) -> impl IntoView {
                     {move || projections_count.get().to_string()}
                 </Badge>
             </button>
+
+            <button
+                class="page__tab"
+                class:page__tab--active=move || active_tab.get() == "history"
+                on:click=move |_| vm.set_tab("history")
+            >
+                "История"
+            </button>
         </div>
     }
 }
@@ -287,6 +296,7 @@ fn TabContent(vm: WbAdvertDailyDetailsVm) -> impl IntoView {
     let vm_attr = vm.clone();
     let vm_journal = vm.clone();
     let vm_projections = vm.clone();
+    let document_id = vm.id;
 
     view! {
         {move || match active_tab.get() {
@@ -295,6 +305,7 @@ fn TabContent(vm: WbAdvertDailyDetailsVm) -> impl IntoView {
             "linked_orders" => view! { <AttributionTab vm=vm_attr.clone()        /> }.into_any(),
             "journal"       => view! { <JournalTab     vm=vm_journal.clone()     /> }.into_any(),
             "projections"   => view! { <ProjectionsTab vm=vm_projections.clone() /> }.into_any(),
+            "history"       => view! { <DocumentHistoryPanel entity_type="a026_wb_advert_daily" document_id=document_id /> }.into_any(),
             _               => view! { <GeneralTab     vm=vm_general.clone()     /> }.into_any(),
         }}
     }
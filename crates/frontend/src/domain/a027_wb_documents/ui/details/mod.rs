@@ -7,6 +7,7 @@ use crate::shared::export::{export_to_excel, ExcelExportable};
 use crate::shared::icons::icon;
 use crate::shared::page_frame::PageFrame;
 use crate::shared::page_standard::PAGE_CAT_DETAIL;
+use crate::system::document_audit::ui::DocumentHistoryPanel;
 use crate::system::favorites::ui::FavoriteButton;
 use gloo_net::http::Request;
 use leptos::prelude::*;
@@ -268,6 +269,13 @@ fn DocumentTabBar(selected_tab: RwSignal<String>) -> impl IntoView {
             >
                 {icon("database")} "Служебное"
             </button>
+            <button
+                class="page__tab"
+                class:page__tab--active=move || selected_tab.get() == "history"
+                on:click=move |_| selected_tab.set("history".to_string())
+            >
+                {icon("clock")} "История"
+            </button>
         </div>
     }
 }
@@ -706,6 +714,12 @@ pub fn WbDocumentsDetail(id: String, #[prop(into)] on_close: Callback<()>) -> im
                                     />
                                 }.into_any(),
                                 "meta" => view! { <MetaTab doc=d.clone() /> }.into_any(),
+                                "history" => view! {
+                                    <DocumentHistoryPanel
+                                        entity_type="a027_wb_documents"
+                                        document_id=Signal::derive(move || doc.get().map(|d| d.id))
+                                    />
+                                }.into_any(),
                                 _ => view! {
                                     <GeneralTab
                                         doc=d.clone()
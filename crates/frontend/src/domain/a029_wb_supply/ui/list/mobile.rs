@@ -0,0 +1,73 @@
+//! Телефонная версия списка: рабочий список поставок для склада.
+//!
+//! Поиск по ID поставки / названию и карточки поставок; нажатие открывает поставку.
+
+use super::{cargo_type_label, format_date, WbSupplyDto};
+use leptos::prelude::*;
+
+#[component]
+pub fn SupplyMobileList(
+    items: Signal<Vec<WbSupplyDto>>,
+    search_query: RwSignal<String>,
+    /// Применить поиск (перезагрузка списка с первой страницы)
+    on_search: Callback<()>,
+    /// `(id, supply_id)` выбранной поставки
+    on_open: Callback<(String, String)>,
+) -> impl IntoView {
+    view! {
+        <div class="mobile-cards">
+            <input
+                type="search"
+                class="mobile-cards__search"
+                placeholder="ID поставки или название"
+                prop:value=move || search_query.get()
+                on:input=move |ev| search_query.set(event_target_value(&ev))
+                on:keydown=move |ev| {
+                    if ev.key() == "Enter" {
+                        on_search.run(());
+                    }
+                }
+            />
+            {move || {
+                let items = items.get();
+                if items.is_empty() {
+                    return view! { <div class="mobile-cards__empty">"Поставок не найдено"</div> }
+                        .into_any();
+                }
+                items
+                    .into_iter()
+                    .map(|item| {
+                        let open = (item.id.clone(), item.supply_id.clone());
+                        let created = item
+                            .created_at_wb
+                            .as_deref()
+                            .map(format_date)
+                            .unwrap_or_else(|| "—".to_string());
+                        view! {
+                            <button class="mobile-card" on:click=move |_| on_open.run(open.clone())>
+                                <div class="mobile-card__top">
+                                    <span class="mobile-card__code">{item.supply_id.clone()}</span>
+                                    <span class="badge badge--primary">
+                                        {if item.is_done { "Завершена" } else { "Открыта" }}
+                                    </span>
+                                </div>
+                                <div class="mobile-card__title">
+                                    {item.supply_name.clone().unwrap_or_else(|| "—".to_string())}
+                                </div>
+                                <div class="mobile-card__line">
+                                    {format!(
+                                        "{} · {} · заказов: {}",
+                                        created,
+                                        cargo_type_label(item.cargo_type),
+                                        item.orders_count
+                                    )}
+                                </div>
+                            </button>
+                        }
+                    })
+                    .collect_view()
+                    .into_any()
+            }}
+        </div>
+    }
+}
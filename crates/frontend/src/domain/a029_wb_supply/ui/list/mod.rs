@@ -1,7 +1,10 @@
+mod mobile;
 pub mod state;
 
+use self::mobile::SupplyMobileList;
 use self::state::create_state;
 use crate::layout::global_context::AppGlobalContext;
+use crate::layout::responsive::use_is_phone;
 use crate::shared::api_utils::api_base;
 use crate::shared::components::close_page_button::ClosePageButton;
 use crate::shared::components::date_range_picker::DateRangePicker;
@@ -275,6 +278,13 @@ pub fn WbSupplyList() -> impl IntoView {
         load_supplies();
     };
 
+    let on_search = Callback::new(move |()| {
+        state.update(|s| s.page = 0);
+        load_supplies();
+    });
+    let on_open = Callback::new(move |(id, supply_id)| open_detail(id, supply_id));
+    let is_phone = use_is_phone();
+
     view! {
         <PageFrame page_id="a029_wb_supply--list" category="list">
             <div class="page__header">
@@ -312,224 +322,236 @@ pub fn WbSupplyList() -> impl IntoView {
             </div>
 
             <div class="page__content">
-                <div class="filter-panel">
-                    <div class="filter-panel-header">
-                        <div class="filter-panel-header__left">
-                            {icon("filter")}
-                            <span class="filter-panel__title">"Фильтры"</span>
-                            {move || {
-                                let count = active_filters_count.get();
-                                if count > 0 {
-                                    view! { <span class="filter-panel__badge">{count}</span> }.into_any()
-                                } else {
-                                    view! { <></> }.into_any()
-                                }
-                            }}
-                        </div>
-                        <div class="filter-panel-header__center">
-                            <PaginationControls
-                                current_page=Signal::derive(move || state.get().page)
-                                total_pages=Signal::derive(move || state.get().total_pages)
-                                total_count=Signal::derive(move || state.get().total_count)
-                                page_size=Signal::derive(move || state.get().page_size)
-                                on_page_change=Callback::new(go_to_page)
-                                on_page_size_change=Callback::new(change_page_size)
-                                page_size_options=vec![50, 100, 200, 500]
-                            />
-                        </div>
-                        <div class="filter-panel-header__right">
-                        </div>
-                    </div>
-
-                    <div class="filter-panel-content">
-                        <Flex gap=FlexGap::Small align=FlexAlign::End style="flex-wrap: wrap;">
-                            <DateRangePicker
-                                date_from=Signal::derive(move || state.with(|s| s.date_from.clone()))
-                                date_to=Signal::derive(move || state.with(|s| s.date_to.clone()))
-                                on_change=Callback::new(move |(from, to)| {
-                                    state.update(|s| {
-                                        s.date_from = from;
-                                        s.date_to = to;
-                                        s.page = 0;
-                                    });
-                                    load_supplies();
-                                })
-                                label="Период:".to_string()
-                            />
-
-                            <div style="width: 260px;">
-                                <Flex vertical=true gap=FlexGap::Small>
-                                    <Label>"Организация:"</Label>
-                                    <Select value=selected_org_id>
-                                        <option value="">"Все организации"</option>
-                                        {move || organizations.get().into_iter().map(|org| {
-                                            let id = org.id.clone();
-                                            view! {
-                                                <option value=id>{org.description}</option>
-                                            }
-                                        }).collect_view()}
-                                    </Select>
-                                </Flex>
+                <Show
+                    when=move || !is_phone.get()
+                    fallback=move || view! {
+                        <SupplyMobileList
+                            items=Signal::derive(move || state.get().supplies)
+                            search_query=search_query
+                            on_search=on_search
+                            on_open=on_open
+                        />
+                    }
+                >
+                    <div class="filter-panel">
+                        <div class="filter-panel-header">
+                            <div class="filter-panel-header__left">
+                                {icon("filter")}
+                                <span class="filter-panel__title">"Фильтры"</span>
+                                {move || {
+                                    let count = active_filters_count.get();
+                                    if count > 0 {
+                                        view! { <span class="filter-panel__badge">{count}</span> }.into_any()
+                                    } else {
+                                        view! { <></> }.into_any()
+                                    }
+                                }}
                             </div>
-
-                            <div style="flex: 1; max-width: 300px;">
-                                <Flex vertical=true gap=FlexGap::Small>
-                                    <Label>"Поиск:"</Label>
-                                    <Input
-                                        value=search_query
-                                        placeholder="ID поставки, название..."
-                                    />
-                                </Flex>
+                            <div class="filter-panel-header__center">
+                                <PaginationControls
+                                    current_page=Signal::derive(move || state.get().page)
+                                    total_pages=Signal::derive(move || state.get().total_pages)
+                                    total_count=Signal::derive(move || state.get().total_count)
+                                    page_size=Signal::derive(move || state.get().page_size)
+                                    on_page_change=Callback::new(go_to_page)
+                                    on_page_size_change=Callback::new(change_page_size)
+                                    page_size_options=vec![50, 100, 200, 500]
+                                />
                             </div>
-
-                            <div style="width: 180px;">
-                                <Flex vertical=true gap=FlexGap::Small>
-                                    <Label>" "</Label>
-                                    <Checkbox
-                                        checked=show_done
-                                        label="Показать завершённые"
-                                    />
-                                </Flex>
+                            <div class="filter-panel-header__right">
                             </div>
+                        </div>
 
-                            <Button
-                                appearance=ButtonAppearance::Secondary
-                                on_click=move |_| {
-                                    state.update(|s| s.page = 0);
-                                    load_supplies();
-                                }
-                            >
-                                "Применить"
-                            </Button>
-                        </Flex>
+                        <div class="filter-panel-content">
+                            <Flex gap=FlexGap::Small align=FlexAlign::End style="flex-wrap: wrap;">
+                                <DateRangePicker
+                                    date_from=Signal::derive(move || state.with(|s| s.date_from.clone()))
+                                    date_to=Signal::derive(move || state.with(|s| s.date_to.clone()))
+                                    on_change=Callback::new(move |(from, to)| {
+                                        state.update(|s| {
+                                            s.date_from = from;
+                                            s.date_to = to;
+                                            s.page = 0;
+                                        });
+                                        load_supplies();
+                                    })
+                                    label="Период:".to_string()
+                                />
+
+                                <div style="width: 260px;">
+                                    <Flex vertical=true gap=FlexGap::Small>
+                                        <Label>"Организация:"</Label>
+                                        <Select value=selected_org_id>
+                                            <option value="">"Все организации"</option>
+                                            {move || organizations.get().into_iter().map(|org| {
+                                                let id = org.id.clone();
+                                                view! {
+                                                    <option value=id>{org.description}</option>
+                                                }
+                                            }).collect_view()}
+                                        </Select>
+                                    </Flex>
+                                </div>
+
+                                <div style="flex: 1; max-width: 300px;">
+                                    <Flex vertical=true gap=FlexGap::Small>
+                                        <Label>"Поиск:"</Label>
+                                        <Input
+                                            value=search_query
+                                            placeholder="ID поставки, название..."
+                                        />
+                                    </Flex>
+                                </div>
+
+                                <div style="width: 180px;">
+                                    <Flex vertical=true gap=FlexGap::Small>
+                                        <Label>" "</Label>
+                                        <Checkbox
+                                            checked=show_done
+                                            label="Показать завершённые"
+                                        />
+                                    </Flex>
+                                </div>
+
+                                <Button
+                                    appearance=ButtonAppearance::Secondary
+                                    on_click=move |_| {
+                                        state.update(|s| s.page = 0);
+                                        load_supplies();
+                                    }
+                                >
+                                    "Применить"
+                                </Button>
+                            </Flex>
+                        </div>
                     </div>
-                </div>
 
-                {move || {
-                    error.get().map(|err| {
-                        view! {
-                            <div class="alert alert--error">
-                                {err}
-                            </div>
-                        }
-                    })
-                }}
-
-                <div class="table-wrapper">
-                    <TableCrosshairHighlight table_id=TABLE_ID.to_string() />
-
-                    <Table attr:id=TABLE_ID attr:style="width: 100%; min-width: 900px;">
-                        <TableHeader>
-                            <TableRow>
-                                <TableHeaderCell resizable=false min_width=160.0 class="resizable">
-                                    <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("supply_id")>
-                                        "ID поставки"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "supply_id"))>
-                                            {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "supply_id", state.with(|s| s.sort_ascending))}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-                                <TableHeaderCell resizable=false min_width=180.0 class="resizable">
-                                    <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("supply_name")>
-                                        "Название"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "supply_name"))>
-                                            {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "supply_name", state.with(|s| s.sort_ascending))}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-                                <TableHeaderCell resizable=false min_width=100.0 class="resizable">
-                                    <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("is_done")>
-                                        "Статус"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "is_done"))>
-                                            {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "is_done", state.with(|s| s.sort_ascending))}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-                                <TableHeaderCell resizable=false min_width=120.0 class="resizable">
-                                    "Тип упаковки"
-                                </TableHeaderCell>
-                                <TableHeaderCell resizable=false min_width=110.0 class="resizable">
-                                    <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("created_at_wb")>
-                                        "Дата создания"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "created_at_wb"))>
-                                            {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "created_at_wb", state.with(|s| s.sort_ascending))}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-                                <TableHeaderCell resizable=false min_width=90.0 class="resizable">
-                                    "Время"
-                                </TableHeaderCell>
-                                <TableHeaderCell resizable=false min_width=110.0 class="resizable">
-                                    <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("closed_at_wb")>
-                                        "Дата закрытия"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "closed_at_wb"))>
-                                            {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "closed_at_wb", state.with(|s| s.sort_ascending))}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-                                <TableHeaderCell resizable=false min_width=90.0 class="resizable">
-                                    "Заказов"
-                                </TableHeaderCell>
-                                <TableHeaderCell resizable=false min_width=150.0 class="resizable">
-                                    <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("organization_name")>
-                                        "Организация"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "organization_name"))>
-                                            {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "organization_name", state.with(|s| s.sort_ascending))}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-                            </TableRow>
-                        </TableHeader>
-
-                        <TableBody>
-                            <For
-                                each=move || state.get().supplies
-                                key=|item| item.id.clone()
-                                children=move |supply| {
-                                    let supply_uuid = supply.id.clone();
-                                    let supply_id_for_link = supply.supply_id.clone();
-                                    let supply_id_display = supply.supply_id.clone();
-                                    let supply_name = supply.supply_name.clone().unwrap_or_else(|| "—".to_string());
-                                    let cargo = cargo_type_label(supply.cargo_type);
-                                    let created_date = supply.created_at_wb.as_deref().map(format_date).unwrap_or_else(|| "—".to_string());
-                                    let created_time = supply.created_at_wb.as_deref().map(format_time).unwrap_or_else(|| "—".to_string());
-                                    let closed_date = supply.closed_at_wb.as_deref().map(format_date).unwrap_or_else(|| "—".to_string());
-                                    let org_name = supply.organization_name.clone().unwrap_or_else(|| "—".to_string());
-                                    let is_done = supply.is_done;
-                                    let orders_count = supply.orders_count.to_string();
-
-                                    view! {
-                                        <TableRow>
-                                            <TableCell>
-                                                <TableCellLayout truncate=true>
-                                                    <a
-                                                        href="#"
-                                                        class="table__link"
-                                                        style="color: #0f6cbd; text-decoration: underline;"
-                                                        on:click=move |e| {
-                                                            e.prevent_default();
-                                                            open_detail(supply_uuid.clone(), supply_id_for_link.clone());
-                                                        }
-                                                    >
-                                                        {supply_id_display}
-                                                    </a>
-                                                </TableCellLayout>
-                                            </TableCell>
-                                            <TableCell><TableCellLayout truncate=true>{supply_name}</TableCellLayout></TableCell>
-                                            <TableCell><TableCellLayout>{if is_done { "Завершена" } else { "Открыта" }}</TableCellLayout></TableCell>
-                                            <TableCell><TableCellLayout>{cargo}</TableCellLayout></TableCell>
-                                            <TableCell><TableCellLayout>{created_date}</TableCellLayout></TableCell>
-                                            <TableCell><TableCellLayout>{created_time}</TableCellLayout></TableCell>
-                                            <TableCell><TableCellLayout>{closed_date}</TableCellLayout></TableCell>
-                                            <TableCell><TableCellLayout>{orders_count}</TableCellLayout></TableCell>
-                                            <TableCell><TableCellLayout truncate=true>{org_name}</TableCellLayout></TableCell>
-                                        </TableRow>
+                    {move || {
+                        error.get().map(|err| {
+                            view! {
+                                <div class="alert alert--error">
+                                    {err}
+                                </div>
+                            }
+                        })
+                    }}
+
+                    <div class="table-wrapper">
+                        <TableCrosshairHighlight table_id=TABLE_ID.to_string() />
+
+                        <Table attr:id=TABLE_ID attr:style="width: 100%; min-width: 900px;">
+                            <TableHeader>
+                                <TableRow>
+                                    <TableHeaderCell resizable=false min_width=160.0 class="resizable">
+                                        <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("supply_id")>
+                                            "ID поставки"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "supply_id"))>
+                                                {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "supply_id", state.with(|s| s.sort_ascending))}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+                                    <TableHeaderCell resizable=false min_width=180.0 class="resizable">
+                                        <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("supply_name")>
+                                            "Название"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "supply_name"))>
+                                                {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "supply_name", state.with(|s| s.sort_ascending))}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+                                    <TableHeaderCell resizable=false min_width=100.0 class="resizable">
+                                        <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("is_done")>
+                                            "Статус"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "is_done"))>
+                                                {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "is_done", state.with(|s| s.sort_ascending))}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+                                    <TableHeaderCell resizable=false min_width=120.0 class="resizable">
+                                        "Тип упаковки"
+                                    </TableHeaderCell>
+                                    <TableHeaderCell resizable=false min_width=110.0 class="resizable">
+                                        <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("created_at_wb")>
+                                            "Дата создания"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "created_at_wb"))>
+                                                {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "created_at_wb", state.with(|s| s.sort_ascending))}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+                                    <TableHeaderCell resizable=false min_width=90.0 class="resizable">
+                                        "Время"
+                                    </TableHeaderCell>
+                                    <TableHeaderCell resizable=false min_width=110.0 class="resizable">
+                                        <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("closed_at_wb")>
+                                            "Дата закрытия"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "closed_at_wb"))>
+                                                {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "closed_at_wb", state.with(|s| s.sort_ascending))}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+                                    <TableHeaderCell resizable=false min_width=90.0 class="resizable">
+                                        "Заказов"
+                                    </TableHeaderCell>
+                                    <TableHeaderCell resizable=false min_width=150.0 class="resizable">
+                                        <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("organization_name")>
+                                            "Организация"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "organization_name"))>
+                                                {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "organization_name", state.with(|s| s.sort_ascending))}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+                                </TableRow>
+                            </TableHeader>
+
+                            <TableBody>
+                                <For
+                                    each=move || state.get().supplies
+                                    key=|item| item.id.clone()
+                                    children=move |supply| {
+                                        let supply_uuid = supply.id.clone();
+                                        let supply_id_for_link = supply.supply_id.clone();
+                                        let supply_id_display = supply.supply_id.clone();
+                                        let supply_name = supply.supply_name.clone().unwrap_or_else(|| "—".to_string());
+                                        let cargo = cargo_type_label(supply.cargo_type);
+                                        let created_date = supply.created_at_wb.as_deref().map(format_date).unwrap_or_else(|| "—".to_string());
+                                        let created_time = supply.created_at_wb.as_deref().map(format_time).unwrap_or_else(|| "—".to_string());
+                                        let closed_date = supply.closed_at_wb.as_deref().map(format_date).unwrap_or_else(|| "—".to_string());
+                                        let org_name = supply.organization_name.clone().unwrap_or_else(|| "—".to_string());
+                                        let is_done = supply.is_done;
+                                        let orders_count = supply.orders_count.to_string();
+
+                                        view! {
+                                            <TableRow>
+                                                <TableCell>
+                                                    <TableCellLayout truncate=true>
+                                                        <a
+                                                            href="#"
+                                                            class="table__link"
+                                                            style="color: #0f6cbd; text-decoration: underline;"
+                                                            on:click=move |e| {
+                                                                e.prevent_default();
+                                                                open_detail(supply_uuid.clone(), supply_id_for_link.clone());
+                                                            }
+                                                        >
+                                                            {supply_id_display}
+                                                        </a>
+                                                    </TableCellLayout>
+                                                </TableCell>
+                                                <TableCell><TableCellLayout truncate=true>{supply_name}</TableCellLayout></TableCell>
+                                                <TableCell><TableCellLayout>{if is_done { "Завершена" } else { "Открыта" }}</TableCellLayout></TableCell>
+                                                <TableCell><TableCellLayout>{cargo}</TableCellLayout></TableCell>
+                                                <TableCell><TableCellLayout>{created_date}</TableCellLayout></TableCell>
+                                                <TableCell><TableCellLayout>{created_time}</TableCellLayout></TableCell>
+                                                <TableCell><TableCellLayout>{closed_date}</TableCellLayout></TableCell>
+                                                <TableCell><TableCellLayout>{orders_count}</TableCellLayout></TableCell>
+                                                <TableCell><TableCellLayout truncate=true>{org_name}</TableCellLayout></TableCell>
+                                            </TableRow>
+                                        }
                                     }
-                                }
-                            />
-                        </TableBody>
-                    </Table>
-                </div>
+                                />
+                            </TableBody>
+                        </Table>
+                    </div>
+                </Show>
             </div>
         </PageFrame>
     }
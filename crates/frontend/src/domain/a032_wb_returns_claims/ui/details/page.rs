@@ -3,6 +3,8 @@ use super::view_model::WbReturnsClaimsDetailsVm;
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::icons::icon;
 use crate::shared::page_frame::PageFrame;
+use crate::system::external_refs::ui::ExternalRefsPanel;
+use crate::system::return_dispositions::ui::ReturnDispositionPanel;
 use leptos::prelude::*;
 use thaw::*;
 
@@ -68,6 +70,7 @@ pub fn WbReturnsClaimsDetails(id: String, #[prop(into)] on_close: Callback<()>)
                         view! { <div>"Нет данных"</div> }.into_any()
                     }
                 }}
+                <ReturnDispositionPanel entity_type="a032_wb_returns_claims" document_id=stored_id.get_value() />
             </div>
         </PageFrame>
     }
@@ -159,6 +162,14 @@ fn TabBar(vm: WbReturnsClaimsDetailsVm) -> impl IntoView {
             >
                 {icon("code")} "JSON"
             </button>
+
+            <button
+                class="page__tab"
+                class:page__tab--active=move || active_tab.get() == "external_refs"
+                on:click=move |_| vm.set_tab("external_refs")
+            >
+                {icon("link")} "Внешние ссылки"
+            </button>
         </div>
     }
 }
@@ -168,11 +179,13 @@ fn TabContent(vm: WbReturnsClaimsDetailsVm) -> impl IntoView {
     let active_tab = vm.active_tab;
     let vm_general = vm.clone();
     let vm_json = vm.clone();
+    let document_id = vm.id;
 
     view! {
         {move || match active_tab.get() {
             "general" => view! { <GeneralTab vm=vm_general.clone() /> }.into_any(),
             "json" => view! { <JsonTab vm=vm_json.clone() /> }.into_any(),
+            "external_refs" => view! { <ExternalRefsPanel entity_type="a032_wb_returns_claims" document_id=document_id /> }.into_any(),
             _ => view! { <GeneralTab vm=vm_general.clone() /> }.into_any(),
         }}
     }
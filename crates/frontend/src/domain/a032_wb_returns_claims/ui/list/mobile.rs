@@ -0,0 +1,60 @@
+//! Телефонная версия списка: приёмка возвратов на складе.
+//!
+//! Поиск по nmId / srid / номеру заявки (сканер вводит значение в поле поиска)
+//! и карточки заявок; нажатие открывает заявку.
+
+use super::{format_date, status_label, WbReturnsClaimsListDto};
+use leptos::prelude::*;
+
+#[component]
+pub fn ClaimsMobileList(
+    items: Signal<Vec<WbReturnsClaimsListDto>>,
+    search_query: RwSignal<String>,
+    /// `(id, claim_id)` выбранной заявки
+    on_open: Callback<(String, String)>,
+) -> impl IntoView {
+    view! {
+        <div class="mobile-cards">
+            <input
+                type="search"
+                class="mobile-cards__search"
+                placeholder="nmId, srid или номер заявки"
+                prop:value=move || search_query.get()
+                on:input=move |ev| search_query.set(event_target_value(&ev))
+            />
+            {move || {
+                let items = items.get();
+                if items.is_empty() {
+                    return view! { <div class="mobile-cards__empty">"Заявок не найдено"</div> }
+                        .into_any();
+                }
+                items
+                    .into_iter()
+                    .map(|item| {
+                        let open = (item.id.clone(), item.claim_id.clone());
+                        view! {
+                            <button class="mobile-card" on:click=move |_| on_open.run(open.clone())>
+                                <div class="mobile-card__top">
+                                    <span class="mobile-card__title">
+                                        {item.imt_name.clone().unwrap_or_else(|| "—".to_string())}
+                                    </span>
+                                    <span class="badge badge--primary">{status_label(item.status)}</span>
+                                </div>
+                                <div class="mobile-card__line">
+                                    {format!("nmId {} · {}", item.nm_id, format_date(&item.dt))}
+                                </div>
+                                <div class="mobile-card__line">
+                                    {item.srid.clone().unwrap_or_else(|| item.claim_id.clone())}
+                                </div>
+                                {item.user_comment.clone().map(|comment| view! {
+                                    <div class="mobile-card__line">{comment}</div>
+                                })}
+                            </button>
+                        }
+                    })
+                    .collect_view()
+                    .into_any()
+            }}
+        </div>
+    }
+}
@@ -1,7 +1,10 @@
+mod mobile;
 pub mod state;
 
+use self::mobile::ClaimsMobileList;
 use self::state::create_state;
 use crate::layout::global_context::AppGlobalContext;
+use crate::layout::responsive::use_is_phone;
 use crate::shared::api_utils::api_base;
 use crate::shared::components::close_page_button::ClosePageButton;
 use crate::shared::components::table::TableCrosshairHighlight;
@@ -84,6 +87,9 @@ pub fn WbReturnsClaimsList() -> impl IntoView {
         );
     };
 
+    let on_open = Callback::new(move |(id, claim_id): (String, String)| open_detail(id, claim_id));
+    let is_phone = use_is_phone();
+
     let load_claims = move || {
         spawn_local(async move {
             set_loading.set(true);
@@ -297,303 +303,310 @@ pub fn WbReturnsClaimsList() -> impl IntoView {
             </div>
 
             <div class="page__content">
-                <div class="filter-panel">
-                    <div class="filter-panel-header">
-                        <div
-                            class="filter-panel-header__left"
-                            on:click=move |_| set_is_filter_expanded.update(|e| *e = !*e)
-                        >
-                            <svg
-                                width="16"
-                                height="16"
-                                viewBox="0 0 24 24"
-                                fill="none"
-                                stroke="currentColor"
-                                stroke-width="2"
-                                stroke-linecap="round"
-                                stroke-linejoin="round"
-                                class=move || {
-                                    if is_filter_expanded.get() {
-                                        "filter-panel__chevron filter-panel__chevron--expanded"
+                <Show
+                    when=move || !is_phone.get()
+                    fallback=move || view! {
+                        <ClaimsMobileList items=displayed_items search_query=search_query on_open=on_open />
+                    }
+                >
+                    <div class="filter-panel">
+                        <div class="filter-panel-header">
+                            <div
+                                class="filter-panel-header__left"
+                                on:click=move |_| set_is_filter_expanded.update(|e| *e = !*e)
+                            >
+                                <svg
+                                    width="16"
+                                    height="16"
+                                    viewBox="0 0 24 24"
+                                    fill="none"
+                                    stroke="currentColor"
+                                    stroke-width="2"
+                                    stroke-linecap="round"
+                                    stroke-linejoin="round"
+                                    class=move || {
+                                        if is_filter_expanded.get() {
+                                            "filter-panel__chevron filter-panel__chevron--expanded"
+                                        } else {
+                                            "filter-panel__chevron"
+                                        }
+                                    }
+                                >
+                                    <polyline points="6 9 12 15 18 9"></polyline>
+                                </svg>
+                                {icon("filter")}
+                                <span class="filter-panel__title">"Фильтры"</span>
+                                {move || {
+                                    let count = active_filters_count.get();
+                                    if count > 0 {
+                                        view! { <span class="filter-panel__badge">{count}</span> }.into_any()
                                     } else {
-                                        "filter-panel__chevron"
+                                        view! { <></> }.into_any()
                                     }
-                                }
-                            >
-                                <polyline points="6 9 12 15 18 9"></polyline>
-                            </svg>
-                            {icon("filter")}
-                            <span class="filter-panel__title">"Фильтры"</span>
-                            {move || {
-                                let count = active_filters_count.get();
-                                if count > 0 {
-                                    view! { <span class="filter-panel__badge">{count}</span> }.into_any()
-                                } else {
-                                    view! { <></> }.into_any()
-                                }
-                            }}
-                        </div>
-                        <div class="filter-panel-header__right">
-                            <Button
-                                appearance=ButtonAppearance::Primary
-                                on_click=move |_| load_claims()
-                                disabled=Signal::derive(move || loading.get())
-                            >
-                                {move || if loading.get() { "Загрузка..." } else { "Обновить" }}
-                            </Button>
+                                }}
+                            </div>
+                            <div class="filter-panel-header__right">
+                                <Button
+                                    appearance=ButtonAppearance::Primary
+                                    on_click=move |_| load_claims()
+                                    disabled=Signal::derive(move || loading.get())
+                                >
+                                    {move || if loading.get() { "Загрузка..." } else { "Обновить" }}
+                                </Button>
+                            </div>
                         </div>
+
+                        {move || {
+                            if is_filter_expanded.get() {
+                                view! {
+                                    <div class="filter-panel-content">
+                                        <Flex gap=FlexGap::Small align=FlexAlign::End>
+                                            <div style="flex: 1; max-width: 320px;">
+                                                <Flex vertical=true gap=FlexGap::Small>
+                                                    <Label>"Поиск:"</Label>
+                                                    <Input
+                                                        value=search_query
+                                                        placeholder="ID заявки, nmId, товар, srid, комментарий..."
+                                                    />
+                                                </Flex>
+                                            </div>
+                                            <div style="width: 200px;">
+                                                <Flex vertical=true gap=FlexGap::Small>
+                                                    <Label>" "</Label>
+                                                    <Checkbox
+                                                        checked=show_archived
+                                                        label="Показать архивные"
+                                                    />
+                                                </Flex>
+                                            </div>
+                                        </Flex>
+                                        <Flex vertical=true gap=FlexGap::Small>
+                                            <Label>"Статусы:"</Label>
+                                            <div style="display: flex; flex-wrap: wrap; gap: 8px; align-items: center;">
+                                                <Checkbox checked=status1_on label="Открыта" />
+                                                <Checkbox checked=status2_on label="На рассмотрении" />
+                                                <Checkbox checked=status3_on label="Одобрена" />
+                                                <Checkbox checked=status4_on label="Отклонена" />
+                                                <Checkbox checked=status5_on label="Закрыта" />
+                                            </div>
+                                        </Flex>
+                                    </div>
+                                }
+                                .into_any()
+                            } else {
+                                view! { <></> }.into_any()
+                            }
+                        }}
                     </div>
 
                     {move || {
-                        if is_filter_expanded.get() {
-                            view! {
-                                <div class="filter-panel-content">
-                                    <Flex gap=FlexGap::Small align=FlexAlign::End>
-                                        <div style="flex: 1; max-width: 320px;">
-                                            <Flex vertical=true gap=FlexGap::Small>
-                                                <Label>"Поиск:"</Label>
-                                                <Input
-                                                    value=search_query
-                                                    placeholder="ID заявки, nmId, товар, srid, комментарий..."
-                                                />
-                                            </Flex>
-                                        </div>
-                                        <div style="width: 200px;">
-                                            <Flex vertical=true gap=FlexGap::Small>
-                                                <Label>" "</Label>
-                                                <Checkbox
-                                                    checked=show_archived
-                                                    label="Показать архивные"
-                                                />
-                                            </Flex>
-                                        </div>
-                                    </Flex>
-                                    <Flex vertical=true gap=FlexGap::Small>
-                                        <Label>"Статусы:"</Label>
-                                        <div style="display: flex; flex-wrap: wrap; gap: 8px; align-items: center;">
-                                            <Checkbox checked=status1_on label="Открыта" />
-                                            <Checkbox checked=status2_on label="На рассмотрении" />
-                                            <Checkbox checked=status3_on label="Одобрена" />
-                                            <Checkbox checked=status4_on label="Отклонена" />
-                                            <Checkbox checked=status5_on label="Закрыта" />
-                                        </div>
-                                    </Flex>
-                                </div>
-                            }
-                            .into_any()
+                        if let Some(err) = error.get() {
+                            view! { <div class="alert alert--error">{err}</div> }.into_any()
                         } else {
                             view! { <></> }.into_any()
                         }
                     }}
-                </div>
 
-                {move || {
-                    if let Some(err) = error.get() {
-                        view! { <div class="alert alert--error">{err}</div> }.into_any()
-                    } else {
-                        view! { <></> }.into_any()
-                    }
-                }}
-
-                <div class="table-wrapper">
-                    <TableCrosshairHighlight table_id=TABLE_ID.to_string() />
-
-                    <Table attr:id=TABLE_ID attr:style="width: 100%; min-width: 1200px;">
-                        <TableHeader>
-                            <TableRow>
-                                <TableHeaderCell resizable=false min_width=110.0 class="resizable">
-                                    <div
-                                        class="table__sortable-header"
-                                        style="cursor: pointer;"
-                                        on:click=move |_| toggle_sort("dt")
-                                    >
-                                        "Дата заявки"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "dt"))>
-                                            {move || get_sort_indicator(
-                                                &state.with(|s| s.sort_field.clone()),
-                                                "dt",
-                                                state.with(|s| s.sort_ascending),
-                                            )}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-
-                                <TableHeaderCell resizable=false min_width=220.0 class="resizable">
-                                    "ID заявки WB"
-                                </TableHeaderCell>
-
-                                <TableHeaderCell resizable=false min_width=90.0 class="resizable">
-                                    <div
-                                        class="table__sortable-header"
-                                        style="cursor: pointer;"
-                                        on:click=move |_| toggle_sort("nm_id")
-                                    >
-                                        "nmId"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "nm_id"))>
-                                            {move || get_sort_indicator(
-                                                &state.with(|s| s.sort_field.clone()),
-                                                "nm_id",
-                                                state.with(|s| s.sort_ascending),
-                                            )}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-
-                                <TableHeaderCell resizable=false min_width=180.0 class="resizable">
-                                    "Товар"
-                                </TableHeaderCell>
-
-                                <TableHeaderCell resizable=false min_width=140.0 class="resizable">
-                                    "Статус"
-                                </TableHeaderCell>
-
-                                <TableHeaderCell resizable=false min_width=90.0 class="resizable">
-                                    <div
-                                        class="table__sortable-header"
-                                        style="cursor: pointer;"
-                                        on:click=move |_| toggle_sort("price")
-                                    >
-                                        "Цена"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "price"))>
-                                            {move || get_sort_indicator(
-                                                &state.with(|s| s.sort_field.clone()),
-                                                "price",
-                                                state.with(|s| s.sort_ascending),
-                                            )}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-
-                                <TableHeaderCell resizable=false min_width=110.0 class="resizable">
-                                    <div
-                                        class="table__sortable-header"
-                                        style="cursor: pointer;"
-                                        on:click=move |_| toggle_sort("order_dt")
-                                    >
-                                        "Дата заказа"
-                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "order_dt"))>
-                                            {move || get_sort_indicator(
-                                                &state.with(|s| s.sort_field.clone()),
-                                                "order_dt",
-                                                state.with(|s| s.sort_ascending),
-                                            )}
-                                        </span>
-                                    </div>
-                                </TableHeaderCell>
-
-                                <TableHeaderCell resizable=false min_width=200.0 class="resizable">
-                                    "srid"
-                                </TableHeaderCell>
-
-                                <TableHeaderCell resizable=false min_width=160.0 class="resizable">
-                                    "Организация"
-                                </TableHeaderCell>
-
-                                <TableHeaderCell resizable=false min_width=220.0 class="resizable">
-                                    "Комментарий"
-                                </TableHeaderCell>
-                            </TableRow>
-                        </TableHeader>
-
-                        <TableBody>
-                            <For
-                                each=move || displayed_items.get()
-                                key=|item| item.id.clone()
-                                children=move |item| {
-                                    let item_id = item.id.clone();
-                                    let claim_id = item.claim_id.clone();
-                                    let claim_id_for_link = item.claim_id.clone();
-                                    let id_for_link = item_id.clone();
-                                    let date_str = format_date(&item.dt);
-                                    let nm_id = item.nm_id;
-                                    let imt_name = item.imt_name.clone().unwrap_or_else(|| "—".to_string());
-                                    let badge_text = status_label(item.status);
-                                    let price_str = item.price.map(|p| format!("{:.0}", p)).unwrap_or_else(|| "—".to_string());
-                                    let order_date_str = item.order_dt.as_deref().map(format_date).unwrap_or_else(|| "—".to_string());
-                                    let srid_str = item.srid.clone().unwrap_or_else(|| "—".to_string());
-                                    let org_name_str = item.org_name.clone().unwrap_or_else(|| "—".to_string());
-                                    let comment_str = item.user_comment.clone().unwrap_or_else(|| "—".to_string());
-
-                                    view! {
-                                        <TableRow>
-                                            <TableCell>
-                                                <TableCellLayout>
-                                                    {date_str}
-                                                </TableCellLayout>
-                                            </TableCell>
-
-                                            <TableCell>
-                                                <TableCellLayout truncate=true>
-                                                    <a
-                                                        href="#"
-                                                        class="table__link"
-                                                        on:click=move |e| {
-                                                            e.prevent_default();
-                                                            open_detail(
-                                                                id_for_link.clone(),
-                                                                claim_id_for_link.clone(),
-                                                            );
-                                                        }
-                                                    >
-                                                        {claim_id}
-                                                    </a>
-                                                </TableCellLayout>
-                                            </TableCell>
-
-                                            <TableCell>
-                                                <TableCellLayout>
-                                                    {nm_id}
-                                                </TableCellLayout>
-                                            </TableCell>
-
-                                            <TableCell>
-                                                <TableCellLayout truncate=true>
-                                                    {imt_name}
-                                                </TableCellLayout>
-                                            </TableCell>
-
-                                            <TableCell>
-                                                <TableCellLayout>
-                                                    <span class="status-badge">{badge_text}</span>
-                                                </TableCellLayout>
-                                            </TableCell>
-
-                                            <TableCell>
-                                                <TableCellLayout>
-                                                    {price_str}
-                                                </TableCellLayout>
-                                            </TableCell>
-
-                                            <TableCell>
-                                                <TableCellLayout>
-                                                    {order_date_str}
-                                                </TableCellLayout>
-                                            </TableCell>
-
-                                            <TableCell>
-                                                <TableCellLayout truncate=true>
-                                                    {srid_str}
-                                                </TableCellLayout>
-                                            </TableCell>
-
-                                            <TableCell>
-                                                <TableCellLayout truncate=true>
-                                                    {org_name_str}
-                                                </TableCellLayout>
-                                            </TableCell>
-
-                                            <TableCell>
-                                                <TableCellLayout truncate=true>
-                                                    {comment_str}
-                                                </TableCellLayout>
-                                            </TableCell>
-                                        </TableRow>
+                    <div class="table-wrapper">
+                        <TableCrosshairHighlight table_id=TABLE_ID.to_string() />
+
+                        <Table attr:id=TABLE_ID attr:style="width: 100%; min-width: 1200px;">
+                            <TableHeader>
+                                <TableRow>
+                                    <TableHeaderCell resizable=false min_width=110.0 class="resizable">
+                                        <div
+                                            class="table__sortable-header"
+                                            style="cursor: pointer;"
+                                            on:click=move |_| toggle_sort("dt")
+                                        >
+                                            "Дата заявки"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "dt"))>
+                                                {move || get_sort_indicator(
+                                                    &state.with(|s| s.sort_field.clone()),
+                                                    "dt",
+                                                    state.with(|s| s.sort_ascending),
+                                                )}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+
+                                    <TableHeaderCell resizable=false min_width=220.0 class="resizable">
+                                        "ID заявки WB"
+                                    </TableHeaderCell>
+
+                                    <TableHeaderCell resizable=false min_width=90.0 class="resizable">
+                                        <div
+                                            class="table__sortable-header"
+                                            style="cursor: pointer;"
+                                            on:click=move |_| toggle_sort("nm_id")
+                                        >
+                                            "nmId"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "nm_id"))>
+                                                {move || get_sort_indicator(
+                                                    &state.with(|s| s.sort_field.clone()),
+                                                    "nm_id",
+                                                    state.with(|s| s.sort_ascending),
+                                                )}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+
+                                    <TableHeaderCell resizable=false min_width=180.0 class="resizable">
+                                        "Товар"
+                                    </TableHeaderCell>
+
+                                    <TableHeaderCell resizable=false min_width=140.0 class="resizable">
+                                        "Статус"
+                                    </TableHeaderCell>
+
+                                    <TableHeaderCell resizable=false min_width=90.0 class="resizable">
+                                        <div
+                                            class="table__sortable-header"
+                                            style="cursor: pointer;"
+                                            on:click=move |_| toggle_sort("price")
+                                        >
+                                            "Цена"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "price"))>
+                                                {move || get_sort_indicator(
+                                                    &state.with(|s| s.sort_field.clone()),
+                                                    "price",
+                                                    state.with(|s| s.sort_ascending),
+                                                )}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+
+                                    <TableHeaderCell resizable=false min_width=110.0 class="resizable">
+                                        <div
+                                            class="table__sortable-header"
+                                            style="cursor: pointer;"
+                                            on:click=move |_| toggle_sort("order_dt")
+                                        >
+                                            "Дата заказа"
+                                            <span class=move || state.with(|s| get_sort_class(&s.sort_field, "order_dt"))>
+                                                {move || get_sort_indicator(
+                                                    &state.with(|s| s.sort_field.clone()),
+                                                    "order_dt",
+                                                    state.with(|s| s.sort_ascending),
+                                                )}
+                                            </span>
+                                        </div>
+                                    </TableHeaderCell>
+
+                                    <TableHeaderCell resizable=false min_width=200.0 class="resizable">
+                                        "srid"
+                                    </TableHeaderCell>
+
+                                    <TableHeaderCell resizable=false min_width=160.0 class="resizable">
+                                        "Организация"
+                                    </TableHeaderCell>
+
+                                    <TableHeaderCell resizable=false min_width=220.0 class="resizable">
+                                        "Комментарий"
+                                    </TableHeaderCell>
+                                </TableRow>
+                            </TableHeader>
+
+                            <TableBody>
+                                <For
+                                    each=move || displayed_items.get()
+                                    key=|item| item.id.clone()
+                                    children=move |item| {
+                                        let item_id = item.id.clone();
+                                        let claim_id = item.claim_id.clone();
+                                        let claim_id_for_link = item.claim_id.clone();
+                                        let id_for_link = item_id.clone();
+                                        let date_str = format_date(&item.dt);
+                                        let nm_id = item.nm_id;
+                                        let imt_name = item.imt_name.clone().unwrap_or_else(|| "—".to_string());
+                                        let badge_text = status_label(item.status);
+                                        let price_str = item.price.map(|p| format!("{:.0}", p)).unwrap_or_else(|| "—".to_string());
+                                        let order_date_str = item.order_dt.as_deref().map(format_date).unwrap_or_else(|| "—".to_string());
+                                        let srid_str = item.srid.clone().unwrap_or_else(|| "—".to_string());
+                                        let org_name_str = item.org_name.clone().unwrap_or_else(|| "—".to_string());
+                                        let comment_str = item.user_comment.clone().unwrap_or_else(|| "—".to_string());
+
+                                        view! {
+                                            <TableRow>
+                                                <TableCell>
+                                                    <TableCellLayout>
+                                                        {date_str}
+                                                    </TableCellLayout>
+                                                </TableCell>
+
+                                                <TableCell>
+                                                    <TableCellLayout truncate=true>
+                                                        <a
+                                                            href="#"
+                                                            class="table__link"
+                                                            on:click=move |e| {
+                                                                e.prevent_default();
+                                                                open_detail(
+                                                                    id_for_link.clone(),
+                                                                    claim_id_for_link.clone(),
+                                                                );
+                                                            }
+                                                        >
+                                                            {claim_id}
+                                                        </a>
+                                                    </TableCellLayout>
+                                                </TableCell>
+
+                                                <TableCell>
+                                                    <TableCellLayout>
+                                                        {nm_id}
+                                                    </TableCellLayout>
+                                                </TableCell>
+
+                                                <TableCell>
+                                                    <TableCellLayout truncate=true>
+                                                        {imt_name}
+                                                    </TableCellLayout>
+                                                </TableCell>
+
+                                                <TableCell>
+                                                    <TableCellLayout>
+                                                        <span class="status-badge">{badge_text}</span>
+                                                    </TableCellLayout>
+                                                </TableCell>
+
+                                                <TableCell>
+                                                    <TableCellLayout>
+                                                        {price_str}
+                                                    </TableCellLayout>
+                                                </TableCell>
+
+                                                <TableCell>
+                                                    <TableCellLayout>
+                                                        {order_date_str}
+                                                    </TableCellLayout>
+                                                </TableCell>
+
+                                                <TableCell>
+                                                    <TableCellLayout truncate=true>
+                                                        {srid_str}
+                                                    </TableCellLayout>
+                                                </TableCell>
+
+                                                <TableCell>
+                                                    <TableCellLayout truncate=true>
+                                                        {org_name_str}
+                                                    </TableCellLayout>
+                                                </TableCell>
+
+                                                <TableCell>
+                                                    <TableCellLayout truncate=true>
+                                                        {comment_str}
+                                                    </TableCellLayout>
+                                                </TableCell>
+                                            </TableRow>
+                                        }
                                     }
-                                }
-                            />
-                        </TableBody>
-                    </Table>
-                </div>
+                                />
+                            </TableBody>
+                        </Table>
+                    </div>
+                </Show>
             </div>
         </PageFrame>
     }
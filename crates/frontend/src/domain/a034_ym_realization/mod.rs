@@ -8,6 +8,7 @@ use crate::layout::global_context::AppGlobalContext;
 use crate::shared::api_utils::api_base;
 use crate::shared::components::card_animated::CardAnimated;
 use crate::shared::components::date_range_picker::DateRangePicker;
+use crate::shared::components::sandbox_badge::SandboxBadge;
 use crate::shared::components::table::format_money;
 use crate::shared::icons::icon;
 use crate::shared::list_utils::{get_sort_class, get_sort_indicator};
@@ -64,6 +65,8 @@ struct DetailsDto {
     fetched_at: String,
     is_posted: bool,
     #[serde(default)]
+    is_sandbox: bool,
+    #[serde(default)]
     sales_lines: Vec<YmRealizationLine>,
     #[serde(default)]
     return_lines: Vec<YmRealizationLine>,
@@ -906,6 +909,7 @@ pub fn YmRealizationDetail(id: String, #[prop(into)] on_close: Callback<()>) ->
                             }.into_any()
                         }
                     })}
+                    <SandboxBadge is_sandbox=Signal::derive(move || doc.get().is_some_and(|d| d.is_sandbox)) />
                 </div>
                 <div class="page__header-right">
                     <Button
@@ -9,6 +9,7 @@ use crate::layout::global_context::AppGlobalContext;
 use crate::shared::icons::icon;
 use crate::shared::page_frame::PageFrame;
 use crate::shared::page_standard::PAGE_CAT_DETAIL;
+use crate::system::document_audit::ui::DocumentHistoryPanel;
 use crate::system::favorites::ui::FavoriteButton;
 use leptos::prelude::*;
 use thaw::*;
@@ -188,6 +189,14 @@ fn TabBar(vm: WbSalesFunnelDailyDetailsVm) -> impl IntoView {
                     {move || projections_count.get().to_string()}
                 </Badge>
             </button>
+
+            <button
+                class="page__tab"
+                class:page__tab--active=move || active_tab.get() == "history"
+                on:click=move |_| vm.set_tab("history")
+            >
+                "История"
+            </button>
         </div>
     }
 }
@@ -200,12 +209,14 @@ fn TabContent(vm: WbSalesFunnelDailyDetailsVm) -> impl IntoView {
     let vm_general = vm.clone();
     let vm_lines = vm.clone();
     let vm_projections = vm.clone();
+    let document_id = vm.id;
 
     view! {
         {move || match active_tab.get() {
             "general"     => view! { <GeneralTab vm=vm_general.clone() /> }.into_any(),
             "lines"       => view! { <LinesTab   vm=vm_lines.clone()   /> }.into_any(),
             "projections" => view! { <ProjectionsTab vm=vm_projections.clone() /> }.into_any(),
+            "history"     => view! { <DocumentHistoryPanel entity_type="a036_wb_sales_funnel_daily" document_id=document_id /> }.into_any(),
             _             => view! { <GeneralTab vm=vm_general.clone() /> }.into_any(),
         }}
     }
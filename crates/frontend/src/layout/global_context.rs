@@ -154,6 +154,24 @@ impl AppGlobalContext {
         self.active.set(Some(key.to_string()));
     }
 
+    /// Replace the whole tab set (session restore after reload/login).
+    /// Navigation bookkeeping is reset; the MRU stack follows tab order with
+    /// the active tab last.
+    pub fn replace_tabs(&self, tabs: Vec<Tab>, active: Option<String>) {
+        let active = active
+            .filter(|key| tabs.iter().any(|tab| &tab.key == key))
+            .or_else(|| tabs.last().map(|tab| tab.key.clone()));
+        let mut stack: Vec<String> = tabs.iter().map(|tab| tab.key.clone()).collect();
+        if let Some(active) = &active {
+            stack.retain(|k| k != active);
+            stack.push(active.clone());
+        }
+        self.opener.set(HashMap::new());
+        self.visit_stack.set(stack);
+        self.opened.set(tabs);
+        self.active.set(active);
+    }
+
     pub fn update_tab_title(&self, key: &str, new_title: &str) {
         self.opened.update(|tabs| {
             if let Some(tab) = tabs.iter_mut().find(|t| t.key == key) {
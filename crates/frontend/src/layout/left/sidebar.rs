@@ -106,6 +106,41 @@ fn get_menu_groups() -> Vec<MenuGroup> {
                     tab_label_for_key("d406_wb_sales_funnel"),
                     "filter",
                 ),
+                SidebarItem::new(
+                    "d407_wb_supply_acceptance",
+                    tab_label_for_key("d407_wb_supply_acceptance"),
+                    "package",
+                ),
+                SidebarItem::new(
+                    "d408_pnl_statement",
+                    tab_label_for_key("d408_pnl_statement"),
+                    "bar-chart",
+                ),
+                SidebarItem::new(
+                    "d415_sku_pnl",
+                    tab_label_for_key("d415_sku_pnl"),
+                    "bar-chart",
+                ),
+                SidebarItem::new(
+                    "d409_margin_scenario",
+                    tab_label_for_key("d409_margin_scenario"),
+                    "percent",
+                ),
+                SidebarItem::new(
+                    "d410_sku_launch_cohorts",
+                    tab_label_for_key("d410_sku_launch_cohorts"),
+                    "trending-up",
+                ),
+                SidebarItem::new(
+                    "d411_sales_anomalies",
+                    tab_label_for_key("d411_sales_anomalies"),
+                    "activity",
+                ),
+                SidebarItem::new(
+                    "d412_demand_forecast",
+                    tab_label_for_key("d412_demand_forecast"),
+                    "bar-chart",
+                ),
             ],
             admin_only: false,
         },
@@ -165,6 +200,14 @@ fn get_menu_groups() -> Vec<MenuGroup> {
                     tab_label_for_key("a007_marketplace_product"),
                     "package",
                 ),
+                // d413 reads and relinks a007 products, same scope.
+                SidebarItem {
+                    id: "d413_sku_cross_mapping",
+                    label: tab_label_for_key("d413_sku_cross_mapping"),
+                    icon: "link",
+                    scope_id: Some("a007_marketplace_product"),
+                    admin_only: false,
+                },
                 SidebarItem::with_scope(
                     "a030_wb_advert_campaign",
                     tab_label_for_key("a030_wb_advert_campaign"),
@@ -341,6 +384,11 @@ fn get_menu_groups() -> Vec<MenuGroup> {
                     tab_label_for_key("u508_repost_documents"),
                     "refresh-cw",
                 ),
+                SidebarItem::new(
+                    "d414_import_coverage",
+                    tab_label_for_key("d414_import_coverage"),
+                    "activity",
+                ),
             ],
             admin_only: false,
         },
@@ -533,6 +581,16 @@ fn get_menu_groups() -> Vec<MenuGroup> {
                     tab_label_for_key("p913_wb_advert_order_attr"),
                     "trending-up",
                 ),
+                SidebarItem::new(
+                    "p918_storage_cost_allocation",
+                    tab_label_for_key("p918_storage_cost_allocation"),
+                    "box",
+                ),
+                SidebarItem::new(
+                    "p919_daily_sales_summary",
+                    tab_label_for_key("p919_daily_sales_summary"),
+                    "bar-chart",
+                ),
                 SidebarItem::new(
                     "p914_mp_finance_turnovers",
                     tab_label_for_key("p914_mp_finance_turnovers"),
@@ -543,6 +601,17 @@ fn get_menu_groups() -> Vec<MenuGroup> {
                     tab_label_for_key("a032_wb_returns_claims"),
                     "file-text",
                 ),
+                SidebarItem::new("sys_exports", tab_label_for_key("sys_exports"), "download"),
+                SidebarItem::new(
+                    "sys_export_profiles",
+                    tab_label_for_key("sys_export_profiles"),
+                    "filter",
+                ),
+                SidebarItem::new(
+                    "sys_external_refs",
+                    tab_label_for_key("sys_external_refs"),
+                    "link",
+                ),
             ],
             admin_only: false,
         },
@@ -610,6 +679,11 @@ fn get_menu_groups() -> Vec<MenuGroup> {
                     "table",
                 ),
                 SidebarItem::new("sys_audit", tab_label_for_key("sys_audit"), "shield-check"),
+                SidebarItem::new(
+                    "sys_server_log",
+                    tab_label_for_key("sys_server_log"),
+                    "activity",
+                ),
                 SidebarItem::new(
                     "sys_s3_files",
                     tab_label_for_key("sys_s3_files"),
@@ -620,6 +694,49 @@ fn get_menu_groups() -> Vec<MenuGroup> {
                     tab_label_for_key("sys_raw_storage"),
                     "database",
                 ),
+                SidebarItem::new(
+                    "sys_data_freshness",
+                    tab_label_for_key("sys_data_freshness"),
+                    "clock",
+                ),
+                SidebarItem::new(
+                    "sys_bulk_operations",
+                    tab_label_for_key("sys_bulk_operations"),
+                    "clock",
+                ),
+                SidebarItem::new(
+                    "sys_bulk_import",
+                    tab_label_for_key("sys_bulk_import"),
+                    "upload",
+                ),
+                SidebarItem::new(
+                    "sys_projection_archive",
+                    tab_label_for_key("sys_projection_archive"),
+                    "layers",
+                ),
+                SidebarItem::new(
+                    "sys_projection_snapshots",
+                    tab_label_for_key("sys_projection_snapshots"),
+                    "columns",
+                ),
+                SidebarItem::new("sys_branding", tab_label_for_key("sys_branding"), "tag"),
+                SidebarItem::new(
+                    "sys_announcements",
+                    tab_label_for_key("sys_announcements"),
+                    "megaphone",
+                ),
+                SidebarItem::new("sys_quotas", tab_label_for_key("sys_quotas"), "activity"),
+                SidebarItem::new(
+                    "sys_description_templates",
+                    tab_label_for_key("sys_description_templates"),
+                    "edit",
+                ),
+                SidebarItem::new(
+                    "sys_print_templates",
+                    tab_label_for_key("sys_print_templates"),
+                    "file-text",
+                ),
+                SidebarItem::new("sys_sso", tab_label_for_key("sys_sso"), "shield-check"),
                 SidebarItem::new(
                     "quality_checks",
                     tab_label_for_key("quality_checks"),
@@ -652,140 +769,142 @@ pub fn Sidebar() -> impl IntoView {
     let ctx = use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
     let (auth_state, _) = use_auth();
 
-    // Check admin status once, untracked, for filtering menu groups.
-    // Scopes are also read untracked, they are stable for the session lifetime.
-    let is_admin_untracked = auth_state.with_untracked(|state| {
-        state
-            .user_info
-            .as_ref()
-            .map(|u| u.is_admin)
-            .unwrap_or(false)
-    });
+    // Scopes are stable for the session lifetime; the menu is rebuilt only when
+    // an admin starts or stops a "view as role" preview.
+    let preview_key =
+        Memo::new(move |_| auth_state.with(|s| s.preview.as_ref().map(|p| p.role_id.clone())));
+    let is_admin_effective = move || {
+        preview_key.track();
+        auth_state
+            .with_untracked(|state| state.effective_user().map(|u| u.is_admin).unwrap_or(false))
+    };
 
     let expanded_groups = RwSignal::new(vec![]);
-    let groups = get_menu_groups();
 
     view! {
         <div class="app-sidebar__content">
-            {groups
-                .into_iter()
-                .filter_map(|group| {
-                    let is_admin_only = group.admin_only;
+            {move || {
+                let is_admin_untracked = is_admin_effective();
+                get_menu_groups()
+                    .into_iter()
+                    .filter_map(|group| {
+                        let is_admin_only = group.admin_only;
 
-                    if is_admin_only && !is_admin_untracked {
-                        return None;
-                    }
+                        if is_admin_only && !is_admin_untracked {
+                            return None;
+                        }
 
-                    let visible_items: Vec<SidebarItem> = group
-                        .items
-                        .into_iter()
-                        .filter(|item| {
-                            if item.admin_only && !is_admin_untracked {
-                                return false;
-                            }
-                            match item.scope_id {
-                                None => true,
-                                Some(scope) => has_read_access(auth_state, scope),
-                            }
-                        })
-                        .collect();
+                        let visible_items: Vec<SidebarItem> = group
+                            .items
+                            .into_iter()
+                            .filter(|item| {
+                                if item.admin_only && !is_admin_untracked {
+                                    return false;
+                                }
+                                match item.scope_id {
+                                    None => true,
+                                    Some(scope) => has_read_access(auth_state, scope),
+                                }
+                            })
+                            .collect();
 
-                    // Keep the user-facing settings group visible even if it becomes empty.
-                    if visible_items.is_empty() && group.id != "settings" {
-                        return None;
-                    }
+                        // Keep the user-facing settings group visible even if it becomes empty.
+                        if visible_items.is_empty() && group.id != "settings" {
+                            return None;
+                        }
 
-                    let group_id = group.id.to_string();
-                    let has_children = !visible_items.is_empty();
+                        let group_id = group.id.to_string();
+                        let has_children = !visible_items.is_empty();
 
-                    let group_id_stored = StoredValue::new(group_id.clone());
-                    let group_id_for_exp = group_id.clone();
-                    let group_id_for_click = group_id.clone();
+                        let group_id_stored = StoredValue::new(group_id.clone());
+                        let group_id_for_exp = group_id.clone();
+                        let group_id_for_click = group_id.clone();
 
-                    Some(view! {
-                        <div>
-                            <div
-                                class="app-sidebar__item"
-                                class:app-sidebar__item--active=move || {
-                                    let gid = group_id_stored.get_value();
-                                    !has_children
-                                        && ctx.active.get().as_ref().map(|a| a == &gid).unwrap_or(false)
-                                }
-                                style:padding-left="12px"
-                                on:click=move |_| {
-                                    if has_children {
-                                        let gid = group_id_for_click.clone();
-                                        expanded_groups.update(move |items| {
-                                            if let Some(pos) = items.iter().position(|x| x == &gid) {
-                                                items.remove(pos);
-                                            } else {
-                                                items.push(gid);
-                                            }
-                                        });
-                                    } else {
-                                        ctx.open_tab(group.id, group.label);
+                        Some(view! {
+                            <div>
+                                <div
+                                    class="app-sidebar__item"
+                                    class:app-sidebar__item--active=move || {
+                                        let gid = group_id_stored.get_value();
+                                        !has_children
+                                            && ctx.active.get().as_ref().map(|a| a == &gid).unwrap_or(false)
                                     }
-                                }
-                            >
-                                <div class="app-sidebar__item-content">
-                                    {icon(group.icon)}
-                                    <span>{group.label}</span>
+                                    style:padding-left="12px"
+                                    on:click=move |_| {
+                                        if has_children {
+                                            let gid = group_id_for_click.clone();
+                                            expanded_groups.update(move |items| {
+                                                if let Some(pos) = items.iter().position(|x| x == &gid) {
+                                                    items.remove(pos);
+                                                } else {
+                                                    items.push(gid);
+                                                }
+                                            });
+                                        } else {
+                                            ctx.open_tab(group.id, group.label);
+                                        }
+                                    }
+                                >
+                                    <div class="app-sidebar__item-content">
+                                        {icon(group.icon)}
+                                        <span>{group.label}</span>
+                                    </div>
+                                    {has_children.then(|| {
+                                        let gid_exp = group_id_for_exp.clone();
+                                        view! {
+                                            <div
+                                                class="app-sidebar__chevron"
+                                                class:app-sidebar__chevron--expanded=move || expanded_groups.get().contains(&gid_exp)
+                                            >
+                                                {icon("chevron-right")}
+                                            </div>
+                                        }
+                                    })}
                                 </div>
+
                                 {has_children.then(|| {
-                                    let gid_exp = group_id_for_exp.clone();
+                                    let gid_show = group_id.clone();
+                                    let items_stored = StoredValue::new(visible_items);
                                     view! {
-                                        <div
-                                            class="app-sidebar__chevron"
-                                            class:app-sidebar__chevron--expanded=move || expanded_groups.get().contains(&gid_exp)
-                                        >
-                                            {icon("chevron-right")}
-                                        </div>
+                                        <Show when=move || expanded_groups.get().contains(&gid_show)>
+                                            <div class="app-sidebar__children">
+                                                {items_stored
+                                                    .get_value()
+                                                    .into_iter()
+                                                    .map(|item| {
+                                                        let item_id = StoredValue::new(item.id.to_string());
+                                                        view! {
+                                                            <div
+                                                                class="app-sidebar__item"
+                                                                class:app-sidebar__item--active=move || {
+                                                                    let iid = item_id.get_value();
+                                                                    ctx.active.get().as_ref().map(|a| a == &iid).unwrap_or(false)
+                                                                }
+                                                                style:padding-left="10px"
+                                                                on:click=move |_| {
+                                                                    ctx.open_tab(item.id, item.label);
+                                                                }
+                                                            >
+                                                                <div class="app-sidebar__item-content">
+                                                                    {icon(item.icon)}
+                                                                    <span>{item.label}</span>
+                                                                </div>
+                                                            </div>
+                                                        }
+                                                    })
+                                                    .collect_view()}
+                                            </div>
+                                        </Show>
                                     }
                                 })}
                             </div>
-
-                            {has_children.then(|| {
-                                let gid_show = group_id.clone();
-                                let items_stored = StoredValue::new(visible_items);
-                                view! {
-                                    <Show when=move || expanded_groups.get().contains(&gid_show)>
-                                        <div class="app-sidebar__children">
-                                            {items_stored
-                                                .get_value()
-                                                .into_iter()
-                                                .map(|item| {
-                                                    let item_id = StoredValue::new(item.id.to_string());
-                                                    view! {
-                                                        <div
-                                                            class="app-sidebar__item"
-                                                            class:app-sidebar__item--active=move || {
-                                                                let iid = item_id.get_value();
-                                                                ctx.active.get().as_ref().map(|a| a == &iid).unwrap_or(false)
-                                                            }
-                                                            style:padding-left="10px"
-                                                            on:click=move |_| {
-                                                                ctx.open_tab(item.id, item.label);
-                                                            }
-                                                        >
-                                                            <div class="app-sidebar__item-content">
-                                                                {icon(item.icon)}
-                                                                <span>{item.label}</span>
-                                                            </div>
-                                                        </div>
-                                                    }
-                                                })
-                                                .collect_view()}
-                                        </div>
-                                    </Show>
-                                }
-                            })}
-                        </div>
+                        })
                     })
-                })
-                .collect_view()}
+                    .collect_view()
+            }}
 
             // Плагины — динамическая группа (admin-only)
-            {is_admin_untracked.then(|| view! {
+            {move || is_admin_effective().then(|| view! {
                 <crate::plugins::PluginsSidebarGroup />
             })}
         </div>
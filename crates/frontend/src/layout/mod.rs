@@ -3,13 +3,19 @@ pub mod footer;
 pub mod global_context;
 pub mod header;
 pub mod left;
+pub mod responsive;
 pub mod right;
+pub mod tab_session;
 pub mod tabs;
 pub mod top_header;
 
+use global_context::AppGlobalContext;
 use leptos::prelude::*;
+use responsive::{provide_viewport, Breakpoint};
 use top_header::TopHeader;
 
+use crate::system::environment::ui::EnvironmentBanner;
+
 /// Main application shell with new UI design structure.
 ///
 /// Layout structure (matching bolt-mpi-ui-redesign):
@@ -32,9 +38,31 @@ where
 {
     // Note: Left/Right components get AppGlobalContext internally
     // for sidebar/panel visibility control
+    let ctx = use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
+    let viewport = provide_viewport();
+    let is_phone = move || viewport.breakpoint.get() == Breakpoint::Phone;
+
+    // На телефоне навигация — выезжающая панель поверх контента, правой панели нет:
+    // при переходе на телефонную ширину обе закрываются.
+    Effect::new(move |_| {
+        if is_phone() {
+            ctx.left_open.set(false);
+            ctx.right_open.set(false);
+        }
+    });
+    // Открыли страницу из навигации — панель больше не нужна.
+    Effect::new(move |_| {
+        ctx.active.track();
+        if untrack(is_phone) {
+            ctx.left_open.set(false);
+        }
+    });
 
     view! {
-        <div class="app-layout">
+        <div class=move || viewport.breakpoint.get().layout_class()>
+            // Режим окружения (dev/staging/prod)
+            <EnvironmentBanner />
+
             // Top header with toggle controls
             <TopHeader />
 
@@ -45,6 +73,11 @@ where
                     {left()}
                 </left::Left>
 
+                // Затемнение под выезжающей навигацией (только телефон)
+                <Show when=move || is_phone() && ctx.left_open.get()>
+                    <div class="app-sidebar-backdrop" on:click=move |_| ctx.left_open.set(false)></div>
+                </Show>
+
                 // Main content area
                 <div class="app-main">
                     <center::Center>
@@ -0,0 +1,79 @@
+//! Адаптивная вёрстка: брейкпоинты ширины окна.
+//!
+//! Значения совпадают с `@media` в `static/themes/core/app-shell.css`:
+//! CSS перестраивает оболочку, а страницы через [`use_is_phone`] выбирают
+//! разметку (на телефоне — карточки вместо широких таблиц).
+
+use leptos::prelude::*;
+
+/// Ширина окна (px), до которой включительно раскладка телефонная.
+pub const PHONE_MAX_WIDTH: f64 = 640.0;
+/// Ширина окна (px), до которой включительно раскладка планшетная.
+pub const TABLET_MAX_WIDTH: f64 = 1024.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    Phone,
+    Tablet,
+    Desktop,
+}
+
+impl Breakpoint {
+    pub fn from_width(width: f64) -> Self {
+        if width <= PHONE_MAX_WIDTH {
+            Self::Phone
+        } else if width <= TABLET_MAX_WIDTH {
+            Self::Tablet
+        } else {
+            Self::Desktop
+        }
+    }
+
+    /// Модификатор `app-layout--*` для корневого элемента оболочки.
+    pub fn layout_class(self) -> &'static str {
+        match self {
+            Self::Phone => "app-layout app-layout--phone",
+            Self::Tablet => "app-layout app-layout--tablet",
+            Self::Desktop => "app-layout",
+        }
+    }
+}
+
+/// Текущий брейкпоинт; предоставляется оболочкой ([`provide_viewport`]).
+#[derive(Clone, Copy)]
+pub struct ViewportContext {
+    pub breakpoint: RwSignal<Breakpoint>,
+}
+
+fn current_breakpoint() -> Breakpoint {
+    web_sys::window()
+        .and_then(|w| w.inner_width().ok())
+        .and_then(|w| w.as_f64())
+        .map(Breakpoint::from_width)
+        .unwrap_or(Breakpoint::Desktop)
+}
+
+/// Создаёт контекст брейкпоинта и следит за изменением размера окна.
+pub fn provide_viewport() -> ViewportContext {
+    let ctx = ViewportContext {
+        breakpoint: RwSignal::new(current_breakpoint()),
+    };
+    let handle = window_event_listener(leptos::ev::resize, move |_| {
+        let next = current_breakpoint();
+        if ctx.breakpoint.get_untracked() != next {
+            ctx.breakpoint.set(next);
+        }
+    });
+    on_cleanup(move || handle.remove());
+    provide_context(ctx);
+    ctx
+}
+
+/// Телефонная раскладка? Вне оболочки (логин и т.п.) — всегда `false`.
+pub fn use_is_phone() -> Signal<bool> {
+    let ctx = use_context::<ViewportContext>();
+    Signal::derive(move || {
+        ctx.map(|c| c.breakpoint.get() == Breakpoint::Phone)
+            .unwrap_or(false)
+    })
+}
@@ -0,0 +1,145 @@
+//! Сессия вкладок: открытые вкладки, активная вкладка и активные подвкладки
+//! документов сохраняются в localStorage и восстанавливаются после перезагрузки
+//! страницы или повторного входа.
+//!
+//! Сессия хранится отдельно для каждого пользователя (`tab_session:<user_id>`),
+//! чтобы после входа под другой учётной записью не открывались чужие документы.
+
+use std::collections::HashMap;
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::global_context::{AppGlobalContext, Tab};
+
+const STORAGE_PREFIX: &str = "tab_session:";
+/// Ключи `form_states` с этим префиксом сохраняются вместе с вкладками.
+const SUB_TAB_PREFIX: &str = "sub_tab:";
+/// Больше вкладок не восстанавливаем: каждая при открытии грузит свои данные.
+const MAX_TABS: usize = 30;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoredTab {
+    key: String,
+    title: String,
+    #[serde(default)]
+    pinned: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TabSession {
+    tabs: Vec<StoredTab>,
+    active: Option<String>,
+    /// Ключ вкладки → активная подвкладка.
+    #[serde(default)]
+    sub_tabs: HashMap<String, String>,
+}
+
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+fn load(storage_key: &str) -> Option<TabSession> {
+    let raw = storage()?.get_item(storage_key).ok()??;
+    serde_json::from_str(&raw).ok()
+}
+
+fn save(storage_key: &str, session: &TabSession) {
+    if let (Some(storage), Ok(raw)) = (storage(), serde_json::to_string(session)) {
+        let _ = storage.set_item(storage_key, &raw);
+    }
+}
+
+/// Восстановить вкладки пользователя и дальше сохранять их при каждом изменении.
+///
+/// Вызывается при монтировании основного layout, т.е. после входа; эффект
+/// сохранения живёт, пока пользователь не вышел.
+pub fn restore_and_persist(ctx: AppGlobalContext, user_id: &str) {
+    let storage_key = format!("{}{}", STORAGE_PREFIX, user_id);
+    let session = load(&storage_key).unwrap_or_default();
+
+    let tabs = session
+        .tabs
+        .into_iter()
+        .take(MAX_TABS)
+        .map(|t| Tab {
+            key: t.key,
+            title: t.title,
+            dirty: false,
+            pinned: t.pinned,
+        })
+        .collect::<Vec<_>>();
+    ctx.form_states.update(|states| {
+        states.retain(|key, _| !key.starts_with(SUB_TAB_PREFIX));
+        for (tab_key, sub_tab) in session.sub_tabs {
+            states.insert(
+                format!("{}{}", SUB_TAB_PREFIX, tab_key),
+                serde_json::Value::String(sub_tab),
+            );
+        }
+    });
+    ctx.replace_tabs(tabs, session.active);
+
+    Effect::new(move |_| {
+        let (tabs, open_keys): (Vec<StoredTab>, Vec<String>) = ctx.opened.with(|tabs| {
+            tabs.iter()
+                .map(|t| {
+                    (
+                        StoredTab {
+                            key: t.key.clone(),
+                            title: t.title.clone(),
+                            pinned: t.pinned,
+                        },
+                        t.key.clone(),
+                    )
+                })
+                .unzip()
+        });
+        let sub_tabs = ctx.form_states.with(|states| {
+            states
+                .iter()
+                .filter_map(|(key, value)| {
+                    let tab_key = key.strip_prefix(SUB_TAB_PREFIX)?;
+                    if !open_keys.iter().any(|k| k == tab_key) {
+                        return None;
+                    }
+                    Some((tab_key.to_string(), value.as_str()?.to_string()))
+                })
+                .collect()
+        });
+        save(
+            &storage_key,
+            &TabSession {
+                tabs,
+                active: ctx.active.get(),
+                sub_tabs,
+            },
+        );
+    });
+}
+
+/// Привязать активную подвкладку документа к сессии: восстановить сохранённую
+/// (если она есть среди `allowed`) и запоминать переключения.
+pub fn bind_sub_tab(
+    tab_key: String,
+    active: RwSignal<&'static str>,
+    allowed: &'static [&'static str],
+) {
+    let Some(ctx) = use_context::<AppGlobalContext>() else {
+        return;
+    };
+    let state_key = format!("{}{}", SUB_TAB_PREFIX, tab_key);
+    let saved = ctx
+        .get_form_state(&state_key)
+        .and_then(|v| v.as_str().map(str::to_string));
+    if let Some(tab) = saved.and_then(|s| allowed.iter().find(|t| **t == s)) {
+        active.set(tab);
+    }
+    Effect::new(move |_| {
+        let current = active.get();
+        ctx.set_form_state(
+            state_key.clone(),
+            serde_json::Value::String(current.to_string()),
+        );
+    });
+}
@@ -5,8 +5,11 @@
 
 use crate::dashboards::MetadataDashboard;
 use crate::dashboards::{
-    D401WbFinanceDashboard, MonthlySummaryDashboard, WbAdvertReportDashboard, WbOrderFlowDashboard,
-    WbSalesFunnelDashboard, YmOrderFlowDashboard,
+    D401WbFinanceDashboard, DemandForecastDashboard, ImportCoverageDashboard,
+    MarginScenarioDashboard, MonthlySummaryDashboard, PnlStatementDashboard,
+    SalesAnomaliesDashboard, SkuCrossMappingDashboard, SkuLaunchCohortsDashboard, SkuPnlDashboard,
+    WbAdvertReportDashboard, WbOrderFlowDashboard, WbSalesFunnelDashboard,
+    WbSupplyAcceptanceDashboard, YmOrderFlowDashboard,
 };
 use crate::data_view::ui::{DataViewDetail, DataViewList, FilterRegistryPage};
 use crate::domain::a001_connection_1c::ui::list::Connection1CList;
@@ -21,8 +24,11 @@ use crate::domain::a007_marketplace_product::ui::list::MarketplaceProductList;
 use crate::domain::a008_marketplace_sales::ui::list::MarketplaceSalesList;
 use crate::domain::a009_ozon_returns::ui::details::OzonReturnsDetail;
 use crate::domain::a009_ozon_returns::ui::list::OzonReturnsList;
+use crate::domain::a010_ozon_fbs_posting::ui::details::OzonFbsPostingDetail;
 use crate::domain::a010_ozon_fbs_posting::ui::list::OzonFbsPostingList;
+use crate::domain::a011_ozon_fbo_posting::ui::details::OzonFboPostingDetail;
 use crate::domain::a011_ozon_fbo_posting::ui::list::OzonFboPostingList;
+use crate::domain::a012_wb_sales::ui::compare::WbSalesCompare;
 use crate::domain::a012_wb_sales::ui::details::WbSalesDetail;
 use crate::domain::a012_wb_sales::ui::list::WbSalesList;
 use crate::domain::a013_ym_order::ui::details::YmOrderDetail;
@@ -102,14 +108,30 @@ use crate::projections::p907_ym_payment_report::ui::list::YmPaymentReportList;
 use crate::projections::p908_wb_goods_prices::WbGoodsPricesList;
 use crate::projections::p913_wb_advert_order_attr::ui::list::WbAdvertOrderAttrList;
 use crate::projections::p914_mp_finance_turnovers::ui::list::MpFinanceTurnoverList;
+use crate::projections::p918_storage_cost_allocation::ui::StorageCostAllocationPage;
+use crate::projections::p919_daily_sales_summary::ui::DailySalesSummaryPage;
 use crate::shared::bi_timeline::ui::{BiTimelineInitial, BiTimelinePage};
 use crate::shared::drilldown_report::DrilldownReportPage;
 use crate::shared::knowledge_base::ui::{KnowledgeArticlePage, KnowledgeBaseWorkspace};
 use crate::shared::universal_dashboard::{SchemaBrowser, UniversalDashboard};
+use crate::system::announcements::ui::AnnouncementsPage;
+use crate::system::branding::ui::BrandingPage;
+use crate::system::bulk_import::ui::BulkImportPage;
+use crate::system::bulk_ops::ui::BulkOperationsPage;
+use crate::system::data_freshness::ui::DataFreshnessPage;
+use crate::system::description_templates::ui::DescriptionTemplatesPage;
+use crate::system::export_profiles::ui::ExportProfilesPage;
+use crate::system::exports::ui::MyExportsPage;
+use crate::system::external_refs::ui::ExternalRefsSearchPage;
+use crate::system::notifications::ui::NotificationSettingsPage;
 use crate::system::pages::style_guide::StyleGuidePage;
 use crate::system::pages::thaw_test::ThawTestPage;
+use crate::system::print_templates::ui::PrintTemplatesPage;
+use crate::system::projection_archive::ui::ProjectionArchivePage;
+use crate::system::quotas::ui::QuotasPage;
 use crate::system::raw_storage::ui::RawStoragePage;
 use crate::system::s3::ui::list::S3FilesPage;
+use crate::system::sso::ui::SsoSettingsPage;
 use crate::system::tasks::ui::details::ScheduledTaskDetails;
 use crate::system::tasks::ui::list::ScheduledTaskList;
 use crate::system::users::ui::details::{CreateUserPage, UserDetailsPage};
@@ -374,12 +396,68 @@ pub fn render_tab_content(key: &str, tabs_store: AppGlobalContext) -> AnyView {
 
         // a010: Ozon FBS Postings
         "a010_ozon_fbs_posting" => view! { <OzonFbsPostingList /> }.into_any(),
+        k if k.starts_with("a010_ozon_fbs_posting_details_") => {
+            let id = k
+                .strip_prefix("a010_ozon_fbs_posting_details_")
+                .unwrap()
+                .to_string();
+            log!("✅ Creating OzonFbsPostingDetail with id: {}", id);
+            view! {
+                <OzonFbsPostingDetail
+                    id=id
+                    on_close=Callback::new({
+                        let key_for_close = key_for_close.clone();
+                        move |_| {
+                            tabs_store.close_tab(&key_for_close);
+                        }
+                    })
+                />
+            }
+            .into_any()
+        }
 
         // a011: Ozon FBO Postings
         "a011_ozon_fbo_posting" => view! { <OzonFboPostingList /> }.into_any(),
+        k if k.starts_with("a011_ozon_fbo_posting_details_") => {
+            let id = k
+                .strip_prefix("a011_ozon_fbo_posting_details_")
+                .unwrap()
+                .to_string();
+            log!("✅ Creating OzonFboPostingDetail with id: {}", id);
+            view! {
+                <OzonFboPostingDetail
+                    id=id
+                    on_close=Callback::new({
+                        let key_for_close = key_for_close.clone();
+                        move |_| {
+                            tabs_store.close_tab(&key_for_close);
+                        }
+                    })
+                />
+            }
+            .into_any()
+        }
 
         // a012: Wildberries Sales
         "a012_wb_sales" => view! { <WbSalesList /> }.into_any(),
+        k if k.starts_with("a012_wb_sales_compare_") => {
+            let ids = k
+                .strip_prefix("a012_wb_sales_compare_")
+                .unwrap()
+                .to_string();
+            view! {
+                <WbSalesCompare
+                    ids=ids
+                    on_close=Callback::new({
+                        let key_for_close = key_for_close.clone();
+                        move |_| {
+                            tabs_store.close_tab(&key_for_close);
+                        }
+                    })
+                />
+            }
+            .into_any()
+        }
         k if k.starts_with("a012_wb_sales_details_") => {
             let id = k
                 .strip_prefix("a012_wb_sales_details_")
@@ -1267,6 +1345,14 @@ pub fn render_tab_content(key: &str, tabs_store: AppGlobalContext) -> AnyView {
             log!("✅ Creating WbAdvertOrderAttrList");
             view! { <WbAdvertOrderAttrList /> }.into_any()
         }
+        "p918_storage_cost_allocation" => {
+            log!("✅ Creating StorageCostAllocationPage");
+            view! { <StorageCostAllocationPage /> }.into_any()
+        }
+        "p919_daily_sales_summary" => {
+            log!("✅ Creating DailySalesSummaryPage");
+            view! { <DailySalesSummaryPage /> }.into_any()
+        }
         "p914_mp_finance_turnovers" => {
             log!("✅ Creating MpFinanceTurnoverList");
             view! { <MpFinanceTurnoverList /> }.into_any()
@@ -1316,8 +1402,26 @@ pub fn render_tab_content(key: &str, tabs_store: AppGlobalContext) -> AnyView {
             view! { <crate::system::roles::ui::matrix::RoleMatrixPage /> }.into_any()
         }
         "sys_audit" => view! { <crate::system::audit::AuditPage /> }.into_any(),
+        "sys_server_log" => view! { <crate::system::server_log::ServerLogPage /> }.into_any(),
         "sys_s3_files" => view! { <S3FilesPage /> }.into_any(),
         "sys_raw_storage" => view! { <RawStoragePage /> }.into_any(),
+        "sys_data_freshness" => view! { <DataFreshnessPage /> }.into_any(),
+        "sys_bulk_operations" => view! { <BulkOperationsPage /> }.into_any(),
+        "sys_bulk_import" => view! { <BulkImportPage /> }.into_any(),
+        "sys_projection_archive" => view! { <ProjectionArchivePage /> }.into_any(),
+        "sys_projection_snapshots" => {
+            view! { <crate::system::projection_snapshots::ProjectionSnapshotsPage /> }.into_any()
+        }
+        "sys_branding" => view! { <BrandingPage /> }.into_any(),
+        "sys_announcements" => view! { <AnnouncementsPage /> }.into_any(),
+        "sys_quotas" => view! { <QuotasPage /> }.into_any(),
+        "sys_description_templates" => view! { <DescriptionTemplatesPage /> }.into_any(),
+        "sys_print_templates" => view! { <PrintTemplatesPage /> }.into_any(),
+        "sys_sso" => view! { <SsoSettingsPage /> }.into_any(),
+        "sys_notification_settings" => view! { <NotificationSettingsPage /> }.into_any(),
+        "sys_exports" => view! { <MyExportsPage /> }.into_any(),
+        "sys_export_profiles" => view! { <ExportProfilesPage /> }.into_any(),
+        "sys_external_refs" => view! { <ExternalRefsSearchPage /> }.into_any(),
         k if k.starts_with("sys_role_details_") => {
             let id = k.strip_prefix("sys_role_details_").unwrap().to_string();
             view! { <crate::system::roles::ui::details::RoleDetailsPage role_id=id /> }.into_any()
@@ -1332,6 +1436,13 @@ pub fn render_tab_content(key: &str, tabs_store: AppGlobalContext) -> AnyView {
             let id = k.strip_prefix("sys_task_details_").unwrap().to_string();
             view! { <ScheduledTaskDetails id=id /> }.into_any()
         }
+        k if k.starts_with("sys_task_run_") => {
+            let rest = k.strip_prefix("sys_task_run_").unwrap();
+            let (id, sid) = rest.split_once('_').unwrap_or((rest, ""));
+            let id = id.to_string();
+            let sid = sid.to_string();
+            view! { <ScheduledTaskDetails id=id run_session_id=sid /> }.into_any()
+        }
         "sys_thaw_test" => {
             log!("✅ Creating ThawTestPage");
             view! { <ThawTestPage /> }.into_any()
@@ -1382,6 +1493,42 @@ pub fn render_tab_content(key: &str, tabs_store: AppGlobalContext) -> AnyView {
             log!("✅ Creating WbSalesFunnelDashboard");
             view! { <WbSalesFunnelDashboard /> }.into_any()
         }
+        "d407_wb_supply_acceptance" => {
+            log!("✅ Creating WbSupplyAcceptanceDashboard");
+            view! { <WbSupplyAcceptanceDashboard /> }.into_any()
+        }
+        "d408_pnl_statement" => {
+            log!("✅ Creating PnlStatementDashboard");
+            view! { <PnlStatementDashboard /> }.into_any()
+        }
+        "d409_margin_scenario" => {
+            log!("✅ Creating MarginScenarioDashboard");
+            view! { <MarginScenarioDashboard /> }.into_any()
+        }
+        "d410_sku_launch_cohorts" => {
+            log!("✅ Creating SkuLaunchCohortsDashboard");
+            view! { <SkuLaunchCohortsDashboard /> }.into_any()
+        }
+        "d411_sales_anomalies" => {
+            log!("✅ Creating SalesAnomaliesDashboard");
+            view! { <SalesAnomaliesDashboard /> }.into_any()
+        }
+        "d412_demand_forecast" => {
+            log!("✅ Creating DemandForecastDashboard");
+            view! { <DemandForecastDashboard /> }.into_any()
+        }
+        "d413_sku_cross_mapping" => {
+            log!("✅ Creating SkuCrossMappingDashboard");
+            view! { <SkuCrossMappingDashboard /> }.into_any()
+        }
+        "d414_import_coverage" => {
+            log!("✅ Creating ImportCoverageDashboard");
+            view! { <ImportCoverageDashboard /> }.into_any()
+        }
+        "d415_sku_pnl" => {
+            log!("✅ Creating SkuPnlDashboard");
+            view! { <SkuPnlDashboard /> }.into_any()
+        }
         k if k.starts_with("d402_wb_order_flow_srid_") => {
             let srid = k
                 .strip_prefix("d402_wb_order_flow_srid_")
@@ -34,6 +34,7 @@ pub fn tab_label_for_key(key: &str) -> &'static str {
         "a005_marketplace" => A005.ui.list_name,
         "a006_connection_mp" => A006.ui.list_name,
         "a012_wb_sales" => A012.ui.list_name,
+        k if k.starts_with("a012_wb_sales_compare_") => "Сравнение продаж WB",
         "a013_ym_order" => A013.ui.list_name,
         "a017_llm_agent" => A017.ui.list_name,
         "a038_llm_connection" => A038.ui.list_name,
@@ -115,6 +116,8 @@ pub fn tab_label_for_key(key: &str) -> &'static str {
         "p910_mp_unlinked_turnovers" => "MP Unlinked Turnovers",
         "p911_wb_advert_by_items" => "WB Advert By Items",
         "p913_wb_advert_order_attr" => "Атрибуция расходов WB",
+        "p918_storage_cost_allocation" => "Распределение хранения по SKU",
+        "p919_daily_sales_summary" => "Дневная сводка продаж",
         "p914_mp_finance_turnovers" => "Финансовые обороты (fina)",
         "p905_commission_history" => "WB Commission History",
         "p906_nomenclature_prices" => "Дилерские цены (УТ)",
@@ -124,6 +127,15 @@ pub fn tab_label_for_key(key: &str) -> &'static str {
         "d400_monthly_summary" => "Сводка за месяц",
         "d405_metadata_dashboard" => "Метаданные",
         "d406_wb_sales_funnel" => "Воронка продаж",
+        "d407_wb_supply_acceptance" => "Приёмка поставок WB",
+        "d408_pnl_statement" => "P&L по организациям",
+        "d409_margin_scenario" => "Сценарий маржи (что если)",
+        "d410_sku_launch_cohorts" => "Когорты запусков SKU",
+        "d411_sales_anomalies" => "Аномалии продаж",
+        "d412_demand_forecast" => "Прогноз спроса",
+        "d413_sku_cross_mapping" => "Сопоставление SKU между МП",
+        "d414_import_coverage" => "Покрытие импорта",
+        "d415_sku_pnl" => "P&L по SKU",
         "d401_wb_finance" => "WB Finance",
         "d402_wb_order_flow" => "WB История заказов",
         k if k.starts_with("d402_wb_order_flow_srid_") => "Вся история",
@@ -135,10 +147,27 @@ pub fn tab_label_for_key(key: &str) -> &'static str {
         "sys_roles" => "Роли",
         "sys_roles_matrix" => "Матрица ролей",
         "sys_audit" => "Аудит доступа",
+        "sys_server_log" => "Журнал сервера",
         "sys_raw_storage" => "Настройка raw JSON",
+        "sys_data_freshness" => "Свежесть данных",
+        "sys_bulk_operations" => "История операций",
+        "sys_bulk_import" => "Массовая загрузка",
+        "sys_projection_archive" => "Архив проекций",
+        "sys_projection_snapshots" => "Снимки проекций",
+        "sys_branding" => "Брендирование",
+        "sys_announcements" => "Что нового",
+        "sys_quotas" => "Квоты организаций",
+        "sys_description_templates" => "Шаблоны описаний",
+        "sys_print_templates" => "Печатные формы",
+        "sys_sso" => "Вход через SSO",
+        "sys_notification_settings" => "Уведомления",
+        "sys_exports" => "Мои выгрузки",
+        "sys_export_profiles" => "Профили выгрузок",
+        "sys_external_refs" => "Внешние ссылки",
         "sys_tasks" => "Регламентные задания",
         "sys_task_details" => "Новая задача",
         k if k.starts_with("sys_task_details_") => "Задача",
+        k if k.starts_with("sys_task_run_") => "Запуск задачи",
         "sys_task_type_registry" => "Реестр типов заданий",
         "sys_thaw_test" => "Тест Thaw UI",
         "sys_style_guide" => "Гид по стилям",
@@ -3,17 +3,23 @@
 //! Contains:
 //! - Toggle buttons for sidebar and right panel
 //! - Application title
+//! - Active organization switcher
 //! - User info and actions
 //! - Theme selector
-//! - Notifications and settings buttons
+//! - Batch operations, announcements, notifications and settings buttons
 
 use crate::domain::a018_llm_chat::ui::AiChatHeaderButton;
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::icons::icon;
 use crate::shared::theme::ThemeSelect;
-use crate::system::auth::context::{do_logout, use_auth};
+use crate::system::announcements::ui::AnnouncementsHeaderButton;
+use crate::system::auth::context::{do_logout, stop_role_preview, use_auth};
+use crate::system::branding::context::use_branding;
 use crate::system::favorites::ui::FavoritesHeaderButton;
 use crate::system::history::ui::HistoryHeaderButton;
+use crate::system::notifications::ui::NotificationsHeaderButton;
+use crate::system::operations::ui::OperationsHeaderButton;
+use crate::system::org_context::ui::OrganizationSwitcher;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 
@@ -28,6 +34,7 @@ pub fn TopHeader() -> impl IntoView {
 
     // Get auth context for user info
     let (auth_state, set_auth_state) = use_auth();
+    let branding = use_branding();
 
     let toggle_sidebar = move |_| {
         ctx.toggle_left();
@@ -51,7 +58,10 @@ pub fn TopHeader() -> impl IntoView {
         <div class="app-header">
             // Left section - brand
             <div class="app-header__brand">
-                <span class="app-header__title">"Integrator"</span>
+                {move || branding.with(|b| b.logo_url.clone()).map(|src| view! {
+                    <img class="app-header__logo" src=src alt="" />
+                })}
+                <span class="app-header__title">{move || branding.with(|b| b.product_name.clone())}</span>
             </div>
 
             // Right section - actions
@@ -82,10 +92,14 @@ pub fn TopHeader() -> impl IntoView {
                     }}
                 </button>
 
+                // Batch operations
+                <OperationsHeaderButton />
+
+                // What's new (release announcements)
+                <AnnouncementsHeaderButton />
+
                 // Notifications
-                <button class="app-header__icon-button" title="Уведомления">
-                    {icon("bell")}
-                </button>
+                <NotificationsHeaderButton />
 
                 // Settings
                 <button class="app-header__icon-button" title="Настройки">
@@ -115,6 +129,29 @@ pub fn TopHeader() -> impl IntoView {
                 <FavoritesHeaderButton />
                 <ThemeSelect />
 
+                // Режим «просмотр как роль» — видно всегда, пока он включён
+                {move || auth_state.get().preview.map(|preview| view! {
+                    <div
+                        class="app-header__user"
+                        style="background: var(--color-warning-bg, rgba(245,158,11,0.18)); cursor: default;"
+                        title="Интерфейс показан с правами роли. Запросы к серверу выполняются с вашими правами."
+                    >
+                        {icon("eye")}
+                        <span>{format!("Просмотр как: {}", preview.role_name)}</span>
+                        <button
+                            class="app-header__icon-button"
+                            style="width: 22px; height: 22px;"
+                            on:click=move |_| stop_role_preview(set_auth_state)
+                            title="Выйти из режима просмотра"
+                        >
+                            {icon("x")}
+                        </button>
+                    </div>
+                })}
+
+                // Active organization (scopes document lists and details)
+                <OrganizationSwitcher />
+
                 // User info
                 <div class="app-header__user">
                     {icon("user")}
@@ -42,6 +42,7 @@ pub const COLUMNS: &[MarketplaceColumn] = &[
 const WB_ONLY: &[MarketplaceKind] = &[MarketplaceKind::Wildberries];
 const OZ_ONLY: &[MarketplaceKind] = &[MarketplaceKind::Ozon];
 const YM_ONLY: &[MarketplaceKind] = &[MarketplaceKind::YandexMarket];
+const WB_OZ: &[MarketplaceKind] = &[MarketplaceKind::Wildberries, MarketplaceKind::Ozon];
 
 // ───────────────────────── Заказы ─────────────────────────
 const ORDERS_LINKS: &[NavLink] = &[
@@ -63,6 +64,15 @@ const ORDERS_LINKS: &[NavLink] = &[
         marketplaces: LinkScope::Only(WB_ONLY),
         entity_type: EntityType::Aggregate,
     },
+    NavLink {
+        tab_key: "d407_wb_supply_acceptance",
+        label: "Приёмка поставок WB",
+        annotation: "Недостачи: отгружено по поставке против принятого и проданного WB",
+        icon: "bar-chart-3",
+        scope_id: None,
+        marketplaces: LinkScope::Only(WB_ONLY),
+        entity_type: EntityType::Projection,
+    },
     NavLink {
         tab_key: "a010_ozon_fbs_posting",
         label: "Отправления FBS",
@@ -139,6 +149,42 @@ const SALES_LINKS: &[NavLink] = &[
         marketplaces: LinkScope::Only(YM_ONLY),
         entity_type: EntityType::Aggregate,
     },
+    NavLink {
+        tab_key: "d410_sku_launch_cohorts",
+        label: "Когорты запусков SKU",
+        annotation: "Накопленная выручка и маржа товаров по месяцу запуска, окупаемость новинок",
+        icon: "trending-up",
+        scope_id: None,
+        marketplaces: LinkScope::All,
+        entity_type: EntityType::Projection,
+    },
+    NavLink {
+        tab_key: "d411_sales_anomalies",
+        label: "Аномалии продаж",
+        annotation: "Всплески и провалы дневных продаж по товарам и кабинетам с разбором причин",
+        icon: "activity",
+        scope_id: Some("a007_marketplace_product"),
+        marketplaces: LinkScope::All,
+        entity_type: EntityType::Projection,
+    },
+    NavLink {
+        tab_key: "d412_demand_forecast",
+        label: "Прогноз спроса",
+        annotation: "Недельный прогноз продаж товаров в штуках и его точность против факта",
+        icon: "bar-chart",
+        scope_id: None,
+        marketplaces: LinkScope::All,
+        entity_type: EntityType::Projection,
+    },
+    NavLink {
+        tab_key: "p919_daily_sales_summary",
+        label: "Дневная сводка продаж",
+        annotation: "Заказы, штуки, выручка и комиссии WB и OZON по дням за последние 90 дней (p919)",
+        icon: "bar-chart",
+        scope_id: Some("p919_daily_sales_summary"),
+        marketplaces: LinkScope::Only(WB_OZ),
+        entity_type: EntityType::Projection,
+    },
 ];
 
 // ───────────────────────── Финансы ────────────────────────
@@ -224,6 +270,24 @@ const FINANCE_LINKS: &[NavLink] = &[
         marketplaces: LinkScope::All,
         entity_type: EntityType::Projection,
     },
+    NavLink {
+        tab_key: "d408_pnl_statement",
+        label: "P&L по организациям",
+        annotation: "Помесячный финансовый результат из проводок GL с детализацией и выгрузкой в Excel",
+        icon: "bar-chart-3",
+        scope_id: None,
+        marketplaces: LinkScope::All,
+        entity_type: EntityType::Projection,
+    },
+    NavLink {
+        tab_key: "d415_sku_pnl",
+        label: "P&L по SKU",
+        annotation: "Выручка, комиссия, эквайринг, соинвест и выплата по номенклатуре с изменением к прошлому периоду",
+        icon: "bar-chart-3",
+        scope_id: None,
+        marketplaces: LinkScope::All,
+        entity_type: EntityType::Projection,
+    },
 ];
 
 // ─────────────────── Реклама и продвижение ────────────────
@@ -309,6 +373,15 @@ const ADVERT_LINKS: &[NavLink] = &[
         marketplaces: LinkScope::Only(WB_ONLY),
         entity_type: EntityType::Projection,
     },
+    NavLink {
+        tab_key: "p918_storage_cost_allocation",
+        label: "Распределение хранения по SKU",
+        annotation: "Хранение и приёмка WB по товарам: правила, драйверы и след распределения (p918)",
+        icon: "box",
+        scope_id: Some("p918_storage_cost_allocation"),
+        marketplaces: LinkScope::Only(WB_ONLY),
+        entity_type: EntityType::Projection,
+    },
 ];
 
 // ──────────────────── Цены и каталог ──────────────────────
@@ -322,6 +395,15 @@ const PRICES_LINKS: &[NavLink] = &[
         marketplaces: LinkScope::All,
         entity_type: EntityType::Aggregate,
     },
+    NavLink {
+        tab_key: "d413_sku_cross_mapping",
+        label: "Сопоставление SKU между МП",
+        annotation: "Товары разных маркетплейсов одной номенклатуры и конфликты штрихкодов",
+        icon: "link",
+        scope_id: Some("a007_marketplace_product"),
+        marketplaces: LinkScope::All,
+        entity_type: EntityType::Projection,
+    },
     NavLink {
         tab_key: "p908_wb_goods_prices",
         label: "Цены товаров WB",
@@ -349,6 +431,15 @@ const PRICES_LINKS: &[NavLink] = &[
         marketplaces: LinkScope::All,
         entity_type: EntityType::Projection,
     },
+    NavLink {
+        tab_key: "d409_margin_scenario",
+        label: "Сценарий маржи (что если)",
+        annotation: "Пересчёт маржи SKU при изменении цены, комиссии, логистики и себестоимости",
+        icon: "percent",
+        scope_id: None,
+        marketplaces: LinkScope::Only(WB_ONLY),
+        entity_type: EntityType::Projection,
+    },
 ];
 
 // ────────────────── Настройки и интеграция ────────────────
@@ -398,6 +489,15 @@ const SETTINGS_LINKS: &[NavLink] = &[
         marketplaces: LinkScope::Only(YM_ONLY),
         entity_type: EntityType::UseCase,
     },
+    NavLink {
+        tab_key: "d414_import_coverage",
+        label: "Покрытие импорта",
+        annotation: "Сводки маркетплейсов по дням против загруженных документов: пропущенные дни",
+        icon: "activity",
+        scope_id: None,
+        marketplaces: LinkScope::All,
+        entity_type: EntityType::Projection,
+    },
 ];
 
 pub const BLOCKS: &[NavBlock] = &[
@@ -12,3 +12,5 @@ pub mod p910_mp_unlinked_turnovers;
 pub mod p911_wb_advert_by_items;
 pub mod p913_wb_advert_order_attr;
 pub mod p914_mp_finance_turnovers;
+pub mod p918_storage_cost_allocation;
+pub mod p919_daily_sales_summary;
@@ -10,6 +10,8 @@ use thaw::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
 
+use contracts::projections::p900_mp_sales_register::SALES_REGISTER_SORT_FIELDS;
+
 use crate::shared::components::date_range_picker::DateRangePicker;
 use crate::shared::components::pagination_controls::PaginationControls;
 use crate::shared::components::table::{
@@ -22,6 +24,7 @@ use crate::shared::list_utils::{
     format_number, get_sort_class, get_sort_indicator, sort_list, Sortable,
 };
 use crate::shared::page_frame::PageFrame;
+use crate::shared::registrator_link::{reg_tab_key, reg_type_name};
 use crate::shared::table_utils::{clear_resize_flag, init_column_resize, was_just_resizing};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +41,9 @@ pub struct SalesRegisterDto {
     pub marketplace_product_ref: Option<String>,
     pub nomenclature_ref: Option<String>,
     pub registrator_ref: String,
+    /// Код агрегата документа-источника; `None` — карточки документа нет.
+    #[serde(default)]
+    pub source_type: Option<String>,
     pub event_time_source: String,
     pub sale_date: String,
     pub source_updated_at: Option<String>,
@@ -61,6 +67,8 @@ pub struct SalesRegisterDto {
     pub loaded_at_utc: String,
     pub payload_version: i32,
     pub extra: Option<String>,
+    #[serde(default)]
+    pub warehouse_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -94,6 +102,12 @@ impl Sortable for SalesRegisterDto {
                 .unwrap_or("")
                 .to_lowercase()
                 .cmp(&other.seller_sku.as_deref().unwrap_or("").to_lowercase()),
+            "warehouse_name" => self
+                .warehouse_name
+                .as_deref()
+                .unwrap_or("")
+                .to_lowercase()
+                .cmp(&other.warehouse_name.as_deref().unwrap_or("").to_lowercase()),
             "qty" => self.qty.partial_cmp(&other.qty).unwrap_or(Ordering::Equal),
             "amount_line" => match (&self.amount_line, &other.amount_line) {
                 (Some(a), Some(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
@@ -222,6 +236,14 @@ pub fn SalesRegisterList() -> impl IntoView {
                         s.page_size,
                     )
                 });
+            let (link_status, warehouse, sort_field, sort_ascending) = state.with_untracked(|s| {
+                (
+                    s.link_status.clone(),
+                    s.warehouse.clone(),
+                    s.sort_field.clone(),
+                    s.sort_ascending,
+                )
+            });
             let offset = (page * page_size) as i32;
 
             let mut query_params = format!(
@@ -237,6 +259,25 @@ pub fn SalesRegisterList() -> impl IntoView {
                 query_params.push_str(&format!("&organization_ref={}", organization_ref));
             }
 
+            if !link_status.is_empty() {
+                query_params.push_str(&format!("&link_status={}", link_status));
+            }
+
+            if !warehouse.trim().is_empty() {
+                query_params.push_str(&format!(
+                    "&warehouse={}",
+                    urlencoding::encode(warehouse.trim())
+                ));
+            }
+
+            // Колонки с серверной сортировкой упорядочивают весь набор, а не только страницу
+            if SALES_REGISTER_SORT_FIELDS.contains(&sort_field.as_str()) {
+                query_params.push_str(&format!(
+                    "&sort_by={}&sort_desc={}",
+                    sort_field, !sort_ascending
+                ));
+            }
+
             match fetch_sales(&query_params).await {
                 Ok(data) => {
                     let total_count = data.total_count.max(0) as usize;
@@ -291,6 +332,28 @@ pub fn SalesRegisterList() -> impl IntoView {
         });
     });
 
+    let link_status_value = RwSignal::new(state.get_untracked().link_status.clone());
+    Effect::new(move || {
+        let v = link_status_value.get();
+        untrack(move || {
+            state.update(|s| {
+                s.link_status = v;
+                s.page = 0;
+            });
+        });
+    });
+
+    let warehouse_value = RwSignal::new(state.get_untracked().warehouse.clone());
+    Effect::new(move || {
+        let v = warehouse_value.get();
+        untrack(move || {
+            state.update(|s| {
+                s.warehouse = v;
+                s.page = 0;
+            });
+        });
+    });
+
     let active_filters_count = Signal::derive(move || {
         let s = state.get();
         let mut count = 0;
@@ -306,6 +369,12 @@ pub fn SalesRegisterList() -> impl IntoView {
         if !s.organization_ref.is_empty() {
             count += 1;
         }
+        if !s.link_status.is_empty() {
+            count += 1;
+        }
+        if !s.warehouse.trim().is_empty() {
+            count += 1;
+        }
         count
     });
 
@@ -336,6 +405,10 @@ pub fn SalesRegisterList() -> impl IntoView {
                 s.sort_ascending = true;
             }
         });
+        if SALES_REGISTER_SORT_FIELDS.contains(&field) {
+            state.update(|s| s.page = 0);
+            load_sales();
+        }
     };
 
     // Pagination: go to specific page
@@ -517,6 +590,24 @@ pub fn SalesRegisterList() -> impl IntoView {
                                     </Select>
                                 </Flex>
                             </div>
+
+                            <div style="width: 200px;">
+                                <Flex vertical=true gap=FlexGap::Small>
+                                    <Label>"Номенклатура:"</Label>
+                                    <Select value=link_status_value>
+                                        <option value="">"Все"</option>
+                                        <option value="linked">"Сопоставлена"</option>
+                                        <option value="unlinked">"Не сопоставлена"</option>
+                                    </Select>
+                                </Flex>
+                            </div>
+
+                            <div style="width: 200px;">
+                                <Flex vertical=true gap=FlexGap::Small>
+                                    <Label>"Склад:"</Label>
+                                    <Input value=warehouse_value placeholder="Часть названия (WB)" />
+                                </Flex>
+                            </div>
                         </Flex>
                     </div>
                 </div>
@@ -598,6 +689,14 @@ pub fn SalesRegisterList() -> impl IntoView {
                                                     </span>
                                                 </div>
                                             </TableHeaderCell>
+                                            <TableHeaderCell resizable=false min_width=120.0 class="resizable">
+                                                <div class="table__sortable-header" style="cursor: pointer;" on:click=move |_| toggle_sort("warehouse_name")>
+                                                    "Склад"
+                                                    <span class=move || state.with(|s| get_sort_class(&s.sort_field, "warehouse_name"))>
+                                                        {move || state.with(|s| get_sort_indicator(&s.sort_field, "warehouse_name", s.sort_ascending))}
+                                                    </span>
+                                                </div>
+                                            </TableHeaderCell>
                                             <SortableHeaderCell
                                                 label="Кол-во"
                                                 sort_field="qty"
@@ -664,6 +763,7 @@ pub fn SalesRegisterList() -> impl IntoView {
                                             let document_no = sale.document_no.clone();
                                             let title = sale.title.clone().unwrap_or_else(|| "—".to_string());
                                             let seller_sku = sale.seller_sku.clone().unwrap_or_else(|| "—".to_string());
+                                            let warehouse_name = sale.warehouse_name.clone().unwrap_or_else(|| "—".to_string());
                                             let qty = sale.qty;
                                             let amount_line = sale.amount_line;
                                             let status_norm = sale.status_norm.clone();
@@ -681,15 +781,14 @@ pub fn SalesRegisterList() -> impl IntoView {
                                             let dealer_price_total = sale.dealer_price_ut.map(|d| d * qty);
                                             let profit_value = sale.cost.map(|c| amount_line.unwrap_or(0.0) - c * qty);
 
-                                            let document_type = sale.document_type.clone();
-                                            let registrator_ref = sale.registrator_ref.clone();
                                             let document_no_for_display = document_no.clone();
 
-                                            // Определяем, есть ли детальная страница для данного типа документа
-                                            let has_detail_page = matches!(
-                                                document_type.as_str(),
-                                                "WB_Sales" | "YM_Order" | "OZON_Returns"
-                                            );
+                                            // Ключ вкладки документа-источника; None — карточки для типа нет
+                                            let source_tab = sale.source_type.as_deref().and_then(|source_type| {
+                                                reg_tab_key(source_type, &sale.registrator_ref).map(|key| {
+                                                    (key, format!("{} {}", reg_type_name(source_type), document_no))
+                                                })
+                                            });
 
                                             view! {
                                                 <TableRow>
@@ -697,11 +796,8 @@ pub fn SalesRegisterList() -> impl IntoView {
                                                     <TableCell><TableCellLayout>{marketplace}</TableCellLayout></TableCell>
                                                     <TableCell>
                                                         <TableCellLayout truncate=true>
-                                                            {if has_detail_page {
+                                                            {if let Some((tab_key, tab_title)) = source_tab {
                                                                 let tabs_store_for_click = tabs_store.clone();
-                                                                let doc_type_for_click = document_type.clone();
-                                                                let registrator_ref_for_click = registrator_ref.clone();
-                                                                let document_no_for_title = document_no_for_display.clone();
 
                                                                 view! {
                                                                     <a
@@ -709,24 +805,6 @@ pub fn SalesRegisterList() -> impl IntoView {
                                                                         style="color: var(--colorBrandForeground1); text-decoration: none; cursor: pointer;"
                                                                         on:click=move |ev| {
                                                                             ev.prevent_default();
-
-                                                                            // Формируем ключ таба в зависимости от типа документа
-                                                                            let (tab_key, tab_title) = match doc_type_for_click.as_str() {
-                                                                                "WB_Sales" => (
-                                                                                    format!("a012_wb_sales_details_{}", registrator_ref_for_click),
-                                                                                    format!("WB Sale {}", document_no_for_title.clone())
-                                                                                ),
-                                                                                "YM_Order" => (
-                                                                                    format!("a013_ym_order_details_{}", registrator_ref_for_click),
-                                                                                    format!("YM Order {}", &registrator_ref_for_click[..8])
-                                                                                ),
-                                                                                "OZON_Returns" => (
-                                                                                    format!("a009_ozon_returns_details_{}", registrator_ref_for_click),
-                                                                                    format!("OZON Return {}", &registrator_ref_for_click[..8])
-                                                                                ),
-                                                                                _ => return,
-                                                                            };
-
                                                                             tabs_store_for_click.open_tab(&tab_key, &tab_title);
                                                                         }
                                                                     >
@@ -742,6 +820,7 @@ pub fn SalesRegisterList() -> impl IntoView {
                                                     </TableCell>
                                                     <TableCell><TableCellLayout truncate=true>{title}</TableCellLayout></TableCell>
                                                     <TableCell><TableCellLayout truncate=true>{seller_sku}</TableCellLayout></TableCell>
+                                                    <TableCell><TableCellLayout truncate=true>{warehouse_name}</TableCellLayout></TableCell>
                                                     <TableCell class="text-right">{format_number(qty)}</TableCell>
                                                     <TableCellMoney value=amount_line color_by_sign=false />
                                                     <TableCellMoney value=cost_total color_by_sign=false />
@@ -776,7 +855,7 @@ fn export_to_csv(data: &[SalesRegisterDto]) -> Result<(), String> {
 
     // Заголовок с точкой с запятой как разделитель
     csv.push_str(
-        "Date;Marketplace;Document №;Product;SKU;Qty;Amount;Cost;Dealer Price;Profit;Status;Organization\n",
+        "Date;Marketplace;Document №;Product;SKU;Warehouse;Qty;Amount;Cost;Dealer Price;Profit;Status;Organization\n",
     );
 
     for sale in data {
@@ -786,6 +865,11 @@ fn export_to_csv(data: &[SalesRegisterDto]) -> Result<(), String> {
             .as_deref()
             .unwrap_or("")
             .replace("\"", "\"\"");
+        let warehouse = sale
+            .warehouse_name
+            .as_deref()
+            .unwrap_or("")
+            .replace("\"", "\"\"");
         let amount_line = sale.amount_line.unwrap_or(0.0);
         let org_display = sale
             .organization_name
@@ -814,12 +898,13 @@ fn export_to_csv(data: &[SalesRegisterDto]) -> Result<(), String> {
         };
 
         csv.push_str(&format!(
-            "\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";{};{};{};{};{};\"{}\";\"{}\"\n",
+            "\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";\"{}\";{};{};{};{};{};\"{}\";\"{}\"\n",
             sale.sale_date,
             sale.marketplace,
             sale.document_no,
             title,
             seller_sku,
+            warehouse,
             qty_str,
             amount_str,
             cost_str,
@@ -9,6 +9,10 @@ pub struct SalesRegisterState {
     pub date_to: String,
     pub marketplace: String,
     pub organization_ref: String,
+    /// `linked` / `unlinked`; пусто — все строки
+    pub link_status: String,
+    /// Склад, поиск по вхождению
+    pub warehouse: String,
     pub sort_field: String,
     pub sort_ascending: bool,
     pub is_loaded: bool,
@@ -43,6 +47,8 @@ impl Default for SalesRegisterState {
             date_to: month_end.format("%Y-%m-%d").to_string(),
             marketplace: String::new(),
             organization_ref: String::new(),
+            link_status: String::new(),
+            warehouse: String::new(),
             sort_field: "sale_date".to_string(),
             sort_ascending: false,
             is_loaded: false,
@@ -0,0 +1,125 @@
+//! Телефонная версия: поиск номенклатуры по штрихкоду на складе.
+//!
+//! Сканер вводит штрихкод в поле и отправляет Enter — ищется точное совпадение
+//! по всем источникам, включая неактивные штрихкоды.
+
+use super::{BarcodeListResponse, NomenclatureBarcodeDto};
+use crate::layout::global_context::AppGlobalContext;
+use crate::layout::tabs::{detail_tab_label, pick_identifier};
+use contracts::domain::a004_nomenclature::ENTITY_METADATA as A004;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+async fn lookup(barcode: &str) -> Result<Vec<NomenclatureBarcodeDto>, String> {
+    let url = format!(
+        "/api/p901/barcodes?barcode={}&include_inactive=true&limit=20",
+        urlencoding::encode(barcode)
+    );
+    let response = gloo_net::http::Request::get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Ошибка запроса: {}", e))?;
+    if !response.ok() {
+        return Err(format!("HTTP ошибка: {}", response.status()));
+    }
+    response
+        .json::<BarcodeListResponse>()
+        .await
+        .map(|data| data.barcodes)
+        .map_err(|e| format!("Ошибка парсинга: {}", e))
+}
+
+#[component]
+pub fn BarcodeMobileLookup() -> impl IntoView {
+    let tabs_store = use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
+
+    let query = RwSignal::new(String::new());
+    let results = RwSignal::new(None::<Vec<NomenclatureBarcodeDto>>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    let search = move || {
+        let barcode = query.get_untracked().trim().to_string();
+        if barcode.is_empty() {
+            return;
+        }
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match lookup(&barcode).await {
+                Ok(items) => results.set(Some(items)),
+                Err(e) => error.set(Some(e)),
+            }
+            loading.set(false);
+        });
+    };
+
+    let open_nomenclature = move |item: &NomenclatureBarcodeDto| {
+        let Some(nom_ref) = item.nomenclature_ref.clone() else {
+            return;
+        };
+        let identifier = pick_identifier(
+            None,
+            item.article.as_deref(),
+            item.nomenclature_name.as_deref(),
+            &nom_ref,
+        );
+        let title = detail_tab_label(A004.ui.element_name, identifier);
+        tabs_store.open_tab(&format!("a004_nomenclature_details_{}", nom_ref), &title);
+    };
+
+    view! {
+        <div class="mobile-cards">
+            <input
+                type="search"
+                inputmode="numeric"
+                autofocus=true
+                class="mobile-cards__search"
+                placeholder="Отсканируйте или введите штрихкод"
+                prop:value=move || query.get()
+                on:input=move |ev| query.set(event_target_value(&ev))
+                on:keydown=move |ev| {
+                    if ev.key() == "Enter" {
+                        search();
+                    }
+                }
+            />
+            <button
+                class="button button--primary"
+                on:click=move |_| search()
+                disabled=move || loading.get()
+            >
+                {move || if loading.get() { "Поиск..." } else { "Найти" }}
+            </button>
+            {move || error.get().map(|err| view! { <div class="alert alert--error">{err}</div> })}
+            {move || match results.get() {
+                None => view! { <></> }.into_any(),
+                Some(items) if items.is_empty() => view! {
+                    <div class="mobile-cards__empty">"Штрихкод не найден"</div>
+                }
+                .into_any(),
+                Some(items) => items
+                    .into_iter()
+                    .map(|item| {
+                        let title = item
+                            .nomenclature_name
+                            .clone()
+                            .unwrap_or_else(|| "Номенклатура не сопоставлена".to_string());
+                        let article = item.article.clone().unwrap_or_default();
+                        let status = if item.is_active { "" } else { " · неактивен" };
+                        let line = format!("{}{}", item.source, status);
+                        view! {
+                            <button class="mobile-card" on:click=move |_| open_nomenclature(&item)>
+                                <span class="mobile-card__code">{item.barcode.clone()}</span>
+                                <span class="mobile-card__title">{title}</span>
+                                <span class="mobile-card__line">{article}</span>
+                                <span class="mobile-card__line">{line}</span>
+                            </button>
+                        }
+                    })
+                    .collect_view()
+                    .into_any(),
+            }}
+        </div>
+    }
+}
@@ -1,4 +1,8 @@
+mod mobile;
+
+use self::mobile::BarcodeMobileLookup;
 use crate::layout::global_context::AppGlobalContext;
+use crate::layout::responsive::use_is_phone;
 use crate::layout::tabs::{detail_tab_label, pick_identifier};
 use crate::shared::date_utils::format_datetime;
 use crate::shared::icons::icon;
@@ -46,6 +50,7 @@ enum SortDirection {
 #[component]
 pub fn BarcodesList() -> impl IntoView {
     let tabs_store = use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
+    let is_phone = use_is_phone();
 
     let (barcodes, set_barcodes) = signal(Vec::<NomenclatureBarcodeDto>::new());
     let (loading, set_loading) = signal(false);
@@ -259,303 +264,317 @@ pub fn BarcodesList() -> impl IntoView {
     };
 
     view! {
-        <div id="p901_nomenclature_barcodes--list" data-page-category="legacy" class="document-container">
-            <div class="document-content">
-                <div class="document-inner">
-                    // Заголовок страницы
-                    <div style="display: flex; justify-content: space-between; align-items: center; margin-bottom: var(--spacing-lg); padding-bottom: var(--spacing-md); border-bottom: 1px solid var(--color-border);">
-                        <h2 style="margin: 0; font-size: var(--font-size-xl); color: var(--color-text-primary);">"Штрихкоды номенклатуры"</h2>
-                        <div class="button-group">
-                            <button
-                                class="button button--primary"
-                                on:click=move |_| load_barcodes()
-                            >
-                                "Обновить"
-                            </button>
-                            <button
-                                class="button button--secondary"
-                                on:click=export_to_csv
-                            >
-                                {icon("download")}
-                                "Excel (csv)"
-                            </button>
+        <Show
+            when=move || !is_phone.get()
+            fallback=move || view! {
+                <div id="p901_nomenclature_barcodes--mobile" class="page">
+                    <div class="page__header">
+                        <div class="page__header-left">
+                            <h1 class="page__title">"Поиск по штрихкоду"</h1>
                         </div>
                     </div>
+                    <BarcodeMobileLookup />
+                </div>
+            }
+        >
+            <div id="p901_nomenclature_barcodes--list" data-page-category="legacy" class="document-container">
+                <div class="document-content">
+                    <div class="document-inner">
+                        // Заголовок страницы
+                        <div style="display: flex; justify-content: space-between; align-items: center; margin-bottom: var(--spacing-lg); padding-bottom: var(--spacing-md); border-bottom: 1px solid var(--color-border);">
+                            <h2 style="margin: 0; font-size: var(--font-size-xl); color: var(--color-text-primary);">"Штрихкоды номенклатуры"</h2>
+                            <div class="button-group">
+                                <button
+                                    class="button button--primary"
+                                    on:click=move |_| load_barcodes()
+                                >
+                                    "Обновить"
+                                </button>
+                                <button
+                                    class="button button--secondary"
+                                    on:click=export_to_csv
+                                >
+                                    {icon("download")}
+                                    "Excel (csv)"
+                                </button>
+                            </div>
+                        </div>
 
-            // Фильтры
-            <div class="form-section" style="background: var(--color-background-secondary); padding: var(--spacing-md); border-radius: var(--radius-md); margin-bottom: var(--spacing-lg);">
-                <div style="display: grid; grid-template-columns: repeat(auto-fit, minmax(200px, 1fr)); gap: var(--spacing-md);">
-                    // Поиск по штрихкоду
-                    <div class="form__group">
-                        <label class="form__label">
-                            "Поиск по штрихкоду:"
-                        </label>
-                        <input
-                            class="form__input"
-                            type="text"
-                            placeholder="Введите штрихкод..."
-                            prop:value=move || search_barcode.get()
-                            on:input=move |ev| {
-                                set_search_barcode.set(event_target_value(&ev));
-                            }
-                        />
-                    </div>
-
-                    // Поиск по артикулу
-                    <div class="form__group">
-                        <label class="form__label">
-                            "Поиск по артикулу:"
-                        </label>
-                        <input
-                            class="form__input"
-                            type="text"
-                            placeholder="Введите артикул..."
-                            prop:value=move || search_article.get()
-                            on:input=move |ev| {
-                                set_search_article.set(event_target_value(&ev));
-                            }
-                        />
-                    </div>
-
-                    // Фильтр по источнику
-                    <div class="form__group">
-                        <label class="form__label">
-                            "Источник:"
-                        </label>
-                        <select
-                            class="form__select"
-                            on:change=move |ev| {
-                                set_filter_source.set(event_target_value(&ev));
-                            }
-                        >
-                            <option value="all">"Все"</option>
-                            <option value="1C">"1C"</option>
-                            <option value="OZON">"OZON"</option>
-                            <option value="WB">"WB"</option>
-                            <option value="YM">"YM"</option>
-                        </select>
-                    </div>
-
-                    // Чекбокс неактивных
-                    <div style="display: flex; align-items: flex-end;">
-                        <label class="form__checkbox-wrapper">
+                // Фильтры
+                <div class="form-section" style="background: var(--color-background-secondary); padding: var(--spacing-md); border-radius: var(--radius-md); margin-bottom: var(--spacing-lg);">
+                    <div style="display: grid; grid-template-columns: repeat(auto-fit, minmax(200px, 1fr)); gap: var(--spacing-md);">
+                        // Поиск по штрихкоду
+                        <div class="form__group">
+                            <label class="form__label">
+                                "Поиск по штрихкоду:"
+                            </label>
                             <input
-                                class="form__checkbox"
-                                type="checkbox"
-                                prop:checked=move || include_inactive.get()
-                                on:change=move |ev| {
-                                    set_include_inactive.set(event_target_checked(&ev));
+                                class="form__input"
+                                type="text"
+                                placeholder="Введите штрихкод..."
+                                prop:value=move || search_barcode.get()
+                                on:input=move |ev| {
+                                    set_search_barcode.set(event_target_value(&ev));
                                 }
                             />
-                            <span class="form__checkbox-label">"Показать неактивные"</span>
-                        </label>
-                    </div>
-                </div>
+                        </div>
 
-                <div style="margin-top: var(--spacing-md); display: flex; justify-content: space-between; align-items: center; flex-wrap: wrap; gap: var(--spacing-md);">
-                    <button
-                        class="button button--primary"
-                        on:click=move |_| load_barcodes()
-                    >
-                        "Применить фильтры"
-                    </button>
-
-                    // Пагинация
-                    <div style="display: flex; align-items: center; gap: var(--spacing-md); flex-wrap: wrap;">
-                        <div style="font-size: var(--font-size-sm); color: var(--color-text-secondary);">
-                            {move || {
-                                let current_offset = offset.get();
-                                let current_limit = limit.get();
-                                let total = total_count.get();
-                                let start = if total > 0 { current_offset + 1 } else { 0 };
-                                let end = std::cmp::min(current_offset + current_limit, total);
-                                format!("Показано: {}-{} из {}", start, end, total)
-                            }}
+                        // Поиск по артикулу
+                        <div class="form__group">
+                            <label class="form__label">
+                                "Поиск по артикулу:"
+                            </label>
+                            <input
+                                class="form__input"
+                                type="text"
+                                placeholder="Введите артикул..."
+                                prop:value=move || search_article.get()
+                                on:input=move |ev| {
+                                    set_search_article.set(event_target_value(&ev));
+                                }
+                            />
                         </div>
-                        <div class="button-group">
-                            <button
-                                class="button button--secondary"
-                                on:click=go_to_prev_page
-                                prop:disabled=move || offset.get() == 0
+
+                        // Фильтр по источнику
+                        <div class="form__group">
+                            <label class="form__label">
+                                "Источник:"
+                            </label>
+                            <select
+                                class="form__select"
+                                on:change=move |ev| {
+                                    set_filter_source.set(event_target_value(&ev));
+                                }
                             >
-                                "← Назад"
-                            </button>
-                            <button
-                                class="button button--secondary"
-                                on:click=go_to_next_page
-                                prop:disabled=move || {
+                                <option value="all">"Все"</option>
+                                <option value="1C">"1C"</option>
+                                <option value="OZON">"OZON"</option>
+                                <option value="WB">"WB"</option>
+                                <option value="YM">"YM"</option>
+                            </select>
+                        </div>
+
+                        // Чекбокс неактивных
+                        <div style="display: flex; align-items: flex-end;">
+                            <label class="form__checkbox-wrapper">
+                                <input
+                                    class="form__checkbox"
+                                    type="checkbox"
+                                    prop:checked=move || include_inactive.get()
+                                    on:change=move |ev| {
+                                        set_include_inactive.set(event_target_checked(&ev));
+                                    }
+                                />
+                                <span class="form__checkbox-label">"Показать неактивные"</span>
+                            </label>
+                        </div>
+                    </div>
+
+                    <div style="margin-top: var(--spacing-md); display: flex; justify-content: space-between; align-items: center; flex-wrap: wrap; gap: var(--spacing-md);">
+                        <button
+                            class="button button--primary"
+                            on:click=move |_| load_barcodes()
+                        >
+                            "Применить фильтры"
+                        </button>
+
+                        // Пагинация
+                        <div style="display: flex; align-items: center; gap: var(--spacing-md); flex-wrap: wrap;">
+                            <div style="font-size: var(--font-size-sm); color: var(--color-text-secondary);">
+                                {move || {
                                     let current_offset = offset.get();
                                     let current_limit = limit.get();
                                     let total = total_count.get();
-                                    current_offset + current_limit >= total
-                                }
-                            >
-                                "Вперёд →"
-                            </button>
+                                    let start = if total > 0 { current_offset + 1 } else { 0 };
+                                    let end = std::cmp::min(current_offset + current_limit, total);
+                                    format!("Показано: {}-{} из {}", start, end, total)
+                                }}
+                            </div>
+                            <div class="button-group">
+                                <button
+                                    class="button button--secondary"
+                                    on:click=go_to_prev_page
+                                    prop:disabled=move || offset.get() == 0
+                                >
+                                    "← Назад"
+                                </button>
+                                <button
+                                    class="button button--secondary"
+                                    on:click=go_to_next_page
+                                    prop:disabled=move || {
+                                        let current_offset = offset.get();
+                                        let current_limit = limit.get();
+                                        let total = total_count.get();
+                                        current_offset + current_limit >= total
+                                    }
+                                >
+                                    "Вперёд →"
+                                </button>
+                            </div>
                         </div>
                     </div>
                 </div>
-            </div>
 
-            // Ошибки
-            {move || {
-                if let Some(err) = error.get() {
-                    view! {
-                        <div class="warning-box" style="background: var(--color-error-50); border-color: var(--color-error-100); margin-bottom: var(--spacing-md);">
-                            <span class="warning-box__icon" style="color: var(--color-error);">"⚠"</span>
-                            <span class="warning-box__text" style="color: var(--color-error);">{err}</span>
-                        </div>
-                    }.into_any()
-                } else {
-                    view! { <></> }.into_any()
-                }
-            }}
-
-            // Индикатор загрузки
-            {move || {
-                if loading.get() {
-                    view! {
-                        <div class="info-box" style="text-align: center; margin-bottom: var(--spacing-md);">
-                            <span class="info-box__text">"Загрузка..."</span>
-                        </div>
-                    }.into_any()
-                } else {
-                    view! { <></> }.into_any()
-                }
-            }}
-
-            // Таблица
-            <div class="table">
-                <table class="table__data table--striped">
-                    <thead class="table__head">
-                        <tr>
-                            <th
-                                class="table__header-cell table__header-cell--sortable"
-                                on:click=move |_| handle_column_click(SortColumn::Barcode)
-                            >
-                                "Штрихкод" {sort_indicator(SortColumn::Barcode)}
-                            </th>
-                            <th
-                                class="table__header-cell table__header-cell--sortable"
-                                on:click=move |_| handle_column_click(SortColumn::NomenclatureName)
-                            >
-                                "Наименование" {sort_indicator(SortColumn::NomenclatureName)}
-                            </th>
-                            <th
-                                class="table__header-cell table__header-cell--sortable"
-                                on:click=move |_| handle_column_click(SortColumn::Article)
-                            >
-                                "Артикул" {sort_indicator(SortColumn::Article)}
-                            </th>
-                            <th
-                                class="table__header-cell table__header-cell--sortable"
-                                on:click=move |_| handle_column_click(SortColumn::Source)
-                            >
-                                "Источник" {sort_indicator(SortColumn::Source)}
-                            </th>
-                            <th
-                                class="table__header-cell table__header-cell--sortable"
-                                on:click=move |_| handle_column_click(SortColumn::UpdatedAt)
-                            >
-                                "Обновлено" {sort_indicator(SortColumn::UpdatedAt)}
-                            </th>
-                            <th class="table__header-cell table__header-cell--center">"Активен"</th>
-                        </tr>
-                    </thead>
-                    <tbody>
-                        {move || {
-                            sorted_barcodes().into_iter().map(|item| {
-                                let has_no_nomenclature = item.nomenclature_ref.is_none();
-                                let nomenclature_ref_for_link = item.nomenclature_ref.clone();
-
-                                view! {
-                                    <tr
-                                        class="table__row"
-                                        class:table__row--warning=has_no_nomenclature
-                                    >
-                                        <td class="table__cell" style="font-family: monospace;">
-                                            {item.barcode.clone()}
-                                            {if has_no_nomenclature {
-                                                view! {
-                                                    <span
-                                                        style="margin-left: var(--spacing-xs); padding: 2px 5px; background: var(--color-warning); color: white; font-size: var(--font-size-xs); border-radius: var(--radius-sm);"
-                                                        title="Не привязан к номенклатуре"
-                                                    >
-                                                        "!"
-                                                    </span>
-                                                }.into_any()
-                                            } else {
-                                                view! { <></> }.into_any()
-                                            }}
-                                        </td>
-                                        <td class="table__cell">
-                                            {if let Some(nom_ref) = nomenclature_ref_for_link {
-                                                if let Some(name) = item.nomenclature_name.clone() {
-                                                    let article = item.article.clone();
-                                                    let name_for_tab = name.clone();
-                                                    let nom_ref_for_tab = nom_ref.clone();
+                // Ошибки
+                {move || {
+                    if let Some(err) = error.get() {
+                        view! {
+                            <div class="warning-box" style="background: var(--color-error-50); border-color: var(--color-error-100); margin-bottom: var(--spacing-md);">
+                                <span class="warning-box__icon" style="color: var(--color-error);">"⚠"</span>
+                                <span class="warning-box__text" style="color: var(--color-error);">{err}</span>
+                            </div>
+                        }.into_any()
+                    } else {
+                        view! { <></> }.into_any()
+                    }
+                }}
+
+                // Индикатор загрузки
+                {move || {
+                    if loading.get() {
+                        view! {
+                            <div class="info-box" style="text-align: center; margin-bottom: var(--spacing-md);">
+                                <span class="info-box__text">"Загрузка..."</span>
+                            </div>
+                        }.into_any()
+                    } else {
+                        view! { <></> }.into_any()
+                    }
+                }}
+
+                // Таблица
+                <div class="table">
+                    <table class="table__data table--striped">
+                        <thead class="table__head">
+                            <tr>
+                                <th
+                                    class="table__header-cell table__header-cell--sortable"
+                                    on:click=move |_| handle_column_click(SortColumn::Barcode)
+                                >
+                                    "Штрихкод" {sort_indicator(SortColumn::Barcode)}
+                                </th>
+                                <th
+                                    class="table__header-cell table__header-cell--sortable"
+                                    on:click=move |_| handle_column_click(SortColumn::NomenclatureName)
+                                >
+                                    "Наименование" {sort_indicator(SortColumn::NomenclatureName)}
+                                </th>
+                                <th
+                                    class="table__header-cell table__header-cell--sortable"
+                                    on:click=move |_| handle_column_click(SortColumn::Article)
+                                >
+                                    "Артикул" {sort_indicator(SortColumn::Article)}
+                                </th>
+                                <th
+                                    class="table__header-cell table__header-cell--sortable"
+                                    on:click=move |_| handle_column_click(SortColumn::Source)
+                                >
+                                    "Источник" {sort_indicator(SortColumn::Source)}
+                                </th>
+                                <th
+                                    class="table__header-cell table__header-cell--sortable"
+                                    on:click=move |_| handle_column_click(SortColumn::UpdatedAt)
+                                >
+                                    "Обновлено" {sort_indicator(SortColumn::UpdatedAt)}
+                                </th>
+                                <th class="table__header-cell table__header-cell--center">"Активен"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {move || {
+                                sorted_barcodes().into_iter().map(|item| {
+                                    let has_no_nomenclature = item.nomenclature_ref.is_none();
+                                    let nomenclature_ref_for_link = item.nomenclature_ref.clone();
+
+                                    view! {
+                                        <tr
+                                            class="table__row"
+                                            class:table__row--warning=has_no_nomenclature
+                                        >
+                                            <td class="table__cell" style="font-family: monospace;">
+                                                {item.barcode.clone()}
+                                                {if has_no_nomenclature {
                                                     view! {
-                                                        <a
-                                                            href="#"
-                                                            style="color: var(--color-primary); text-decoration: none; cursor: pointer;"
-                                                            on:click=move |ev| {
-                                                                ev.prevent_default();
-                                                                let tab_key = format!("a004_nomenclature_details_{}", nom_ref_for_tab);
-                                                                let identifier = pick_identifier(
-                                                                    None,
-                                                                    article.as_deref(),
-                                                                    Some(name_for_tab.as_str()),
-                                                                    &nom_ref_for_tab,
-                                                                );
-                                                                let title = detail_tab_label(A004.ui.element_name, identifier);
-                                                                tabs_store.open_tab(&tab_key, &title);
-                                                            }
+                                                        <span
+                                                            style="margin-left: var(--spacing-xs); padding: 2px 5px; background: var(--color-warning); color: white; font-size: var(--font-size-xs); border-radius: var(--radius-sm);"
+                                                            title="Не привязан к номенклатуре"
                                                         >
-                                                            {name}
-                                                        </a>
+                                                            "!"
+                                                        </span>
                                                     }.into_any()
                                                 } else {
-                                                    view! { <span style="color: var(--color-text-tertiary);">"-"</span> }.into_any()
-                                                }
-                                            } else {
-                                                view! {
-                                                    <span style="color: var(--color-warning); font-weight: 500;">
-                                                        "Не привязан"
-                                                    </span>
-                                                }.into_any()
-                                            }}
-                                        </td>
-                                        <td class="table__cell">{item.article.clone().unwrap_or_else(|| "-".to_string())}</td>
-                                        <td class="table__cell">
-                                            <span style={format!("padding: 2px 8px; border-radius: var(--radius-sm); background: {}; color: white; font-size: var(--font-size-xs);",
-                                                match item.source.as_str() {
-                                                    "1C" => "#6c757d",
-                                                    "OZON" => "#0088cc",
-                                                    "WB" => "#8b00ff",
-                                                    "YM" => "#fc0",
-                                                    _ => "#333",
-                                                }
-                                            )}>
-                                                {item.source.clone()}
-                                            </span>
-                                        </td>
-                                        <td class="table__cell" style="font-size: var(--font-size-sm);">{format_datetime(&item.updated_at)}</td>
-                                        <td class="table__cell table__cell--center">
-                                            {if item.is_active {
-                                                view! { <span style="color: var(--color-success); font-weight: bold;">"✓"</span> }.into_any()
-                                            } else {
-                                                view! { <span style="color: var(--color-error); font-weight: bold;">"✗"</span> }.into_any()
-                                            }}
-                                        </td>
-                                    </tr>
-                                }
-                            }).collect_view()
-                        }}
-                    </tbody>
-                </table>
-            </div>
+                                                    view! { <></> }.into_any()
+                                                }}
+                                            </td>
+                                            <td class="table__cell">
+                                                {if let Some(nom_ref) = nomenclature_ref_for_link {
+                                                    if let Some(name) = item.nomenclature_name.clone() {
+                                                        let article = item.article.clone();
+                                                        let name_for_tab = name.clone();
+                                                        let nom_ref_for_tab = nom_ref.clone();
+                                                        view! {
+                                                            <a
+                                                                href="#"
+                                                                style="color: var(--color-primary); text-decoration: none; cursor: pointer;"
+                                                                on:click=move |ev| {
+                                                                    ev.prevent_default();
+                                                                    let tab_key = format!("a004_nomenclature_details_{}", nom_ref_for_tab);
+                                                                    let identifier = pick_identifier(
+                                                                        None,
+                                                                        article.as_deref(),
+                                                                        Some(name_for_tab.as_str()),
+                                                                        &nom_ref_for_tab,
+                                                                    );
+                                                                    let title = detail_tab_label(A004.ui.element_name, identifier);
+                                                                    tabs_store.open_tab(&tab_key, &title);
+                                                                }
+                                                            >
+                                                                {name}
+                                                            </a>
+                                                        }.into_any()
+                                                    } else {
+                                                        view! { <span style="color: var(--color-text-tertiary);">"-"</span> }.into_any()
+                                                    }
+                                                } else {
+                                                    view! {
+                                                        <span style="color: var(--color-warning); font-weight: 500;">
+                                                            "Не привязан"
+                                                        </span>
+                                                    }.into_any()
+                                                }}
+                                            </td>
+                                            <td class="table__cell">{item.article.clone().unwrap_or_else(|| "-".to_string())}</td>
+                                            <td class="table__cell">
+                                                <span style={format!("padding: 2px 8px; border-radius: var(--radius-sm); background: {}; color: white; font-size: var(--font-size-xs);",
+                                                    match item.source.as_str() {
+                                                        "1C" => "#6c757d",
+                                                        "OZON" => "#0088cc",
+                                                        "WB" => "#8b00ff",
+                                                        "YM" => "#fc0",
+                                                        _ => "#333",
+                                                    }
+                                                )}>
+                                                    {item.source.clone()}
+                                                </span>
+                                            </td>
+                                            <td class="table__cell" style="font-size: var(--font-size-sm);">{format_datetime(&item.updated_at)}</td>
+                                            <td class="table__cell table__cell--center">
+                                                {if item.is_active {
+                                                    view! { <span style="color: var(--color-success); font-weight: bold;">"✓"</span> }.into_any()
+                                                } else {
+                                                    view! { <span style="color: var(--color-error); font-weight: bold;">"✗"</span> }.into_any()
+                                                }}
+                                            </td>
+                                        </tr>
+                                    }
+                                }).collect_view()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+                    </div>
                 </div>
             </div>
-        </div>
+        </Show>
     }
 }
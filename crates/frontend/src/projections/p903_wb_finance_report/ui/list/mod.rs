@@ -1,15 +1,21 @@
 mod state;
 
 use crate::layout::global_context::AppGlobalContext;
+use crate::shared::api_utils::api_base;
 use crate::shared::auth_download::download_authenticated_file;
 use crate::shared::components::date_range_picker::DateRangePicker;
 use crate::shared::components::pagination_controls::PaginationControls;
+use crate::shared::components::row_context_menu::{
+    api_url, RawJsonSource, RowAction, RowContextMenu, RowMenuState, RowRef,
+};
 use crate::shared::components::table::{TableCellMoney, TableCrosshairHighlight};
 use crate::shared::icons::icon;
 use crate::shared::list_utils::{format_number, get_sort_class, get_sort_indicator};
 use crate::shared::page_frame::PageFrame;
+use crate::system::exports::api::start_export;
+use crate::system::exports::ui::EXPORTS_TAB_KEY;
 use contracts::projections::p903_wb_finance_report::dto::{
-    WbFinanceReportDto, WbFinanceReportListResponse,
+    WbFinanceReportDto, WbFinanceReportListRequest, WbFinanceReportListResponse,
 };
 use leptos::logging::log;
 use leptos::prelude::*;
@@ -379,25 +385,64 @@ pub fn WbFinanceReportList() -> impl IntoView {
 
         set_is_exporting.set(true);
         leptos::task::spawn_local(async move {
-            let url = build_export_url(
-                &date_from_val,
-                &date_to_val,
-                &nm_id_val,
-                &sa_name_val,
-                &connection_val,
-                &operation_val,
-                &srid_val,
-                &sort_by_val,
+            // Фоновая выгрузка: файл появится в «Мои выгрузки», по готовности придёт уведомление
+            let req = WbFinanceReportListRequest {
+                date_from: date_from_val,
+                date_to: date_to_val,
+                nm_id: nm_id_val.trim().parse::<i64>().ok(),
+                sa_name: Some(sa_name_val.trim().to_string()).filter(|s| !s.is_empty()),
+                connection_mp_ref: Some(connection_val.trim().to_string())
+                    .filter(|s| !s.is_empty()),
+                organization_ref: None,
+                supplier_oper_name: Some(operation_val.trim().to_string())
+                    .filter(|s| !s.is_empty()),
+                srid: Some(srid_val.trim().to_string()).filter(|s| !s.is_empty()),
+                sort_by: sort_by_val,
                 sort_desc,
-            );
+                limit: 0,
+                offset: 0,
+            };
+            match start_export("/api/p903/finance-report/export", &req).await {
+                Ok(_) => tabs_store.open_tab(EXPORTS_TAB_KEY, "Мои выгрузки"),
+                Err(e) => {
+                    log!("Failed to start finance report export: {}", e);
+                    set_error.set(Some(format!("Экспорт CSV: {e}")));
+                }
+            }
+            set_is_exporting.set(false);
+        });
+    };
+
+    // Потоковая выгрузка: CSV скачивается сразу, без очереди «Мои выгрузки»
+    let download_csv = move || {
+        let url = export_url(&state.get_untracked());
+        set_is_exporting.set(true);
+        leptos::task::spawn_local(async move {
             if let Err(e) = download_authenticated_file(&url, "wb_finance_report.csv").await {
-                log!("Failed to export finance report: {}", e);
+                log!("Failed to download finance report CSV: {}", e);
                 set_error.set(Some(format!("Экспорт CSV: {e}")));
             }
             set_is_exporting.set(false);
         });
     };
 
+    // Контекстное меню строк (правый клик)
+    let row_menu = RowMenuState::new();
+    let row_actions = vec![
+        RowAction::new("Открыть", "eye", move |row: RowRef| {
+            let tab_key = format!("p903_wb_finance_report_details_id_{}", encode_q(&row.id));
+            tabs_store.open_tab(&tab_key, &format!("WB Finance #{}", row.title));
+        }),
+        RowAction::copy_id(),
+        RowAction::raw_json(|id| {
+            RawJsonSource::Direct(api_url(&format!(
+                "/api/p903/finance-report/by-id/{}/raw",
+                urlencoding::encode(id)
+            )))
+        })
+        .read("p903_wb_finance_report"),
+    ];
+
     view! {
         <PageFrame page_id="p903_wb_finance_report--list" category="list">
             <div class="page__header">
@@ -414,7 +459,16 @@ pub fn WbFinanceReportList() -> impl IntoView {
                         disabled=move || state.get().total_count == 0 || is_exporting.get()
                     >
                         {icon("download")}
-                        {move || if is_exporting.get() { "Выгрузка…" } else { "Excel (csv)" }}
+                        {move || if is_exporting.get() { "Постановка в очередь…" } else { "Excel (csv)" }}
+                    </Button>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on_click=move |_| download_csv()
+                        disabled=move || state.get().total_count == 0 || is_exporting.get()
+                        attr:title="Скачать CSV сразу, без фоновой выгрузки"
+                    >
+                        {icon("download")}
+                        "Export"
                     </Button>
                 </div>
             </div>
@@ -785,8 +839,9 @@ pub fn WbFinanceReportList() -> impl IntoView {
                                                     let detail_id = item.id.clone();
                                                     let rrd_id_clone = item.rrd_id;
                                                     let connection_id = item.connection_mp_ref.clone();
+                                                    let row_ref = RowRef::new(item.id.clone(), item.rrd_id.to_string());
                                                     view! {
-                                                        <TableRow>
+                                                        <TableRow on:contextmenu=move |ev| row_menu.open_at(&ev, row_ref.clone())>
                                                             <TableCell><TableCellLayout>{item.rr_dt}</TableCellLayout></TableCell>
                                                             <TableCell><TableCellLayout truncate=true>{get_connection_name(&connection_id)}</TableCellLayout></TableCell>
                                                             <TableCell>
@@ -835,6 +890,7 @@ pub fn WbFinanceReportList() -> impl IntoView {
             </div>
         </div>
 
+        <RowContextMenu state=row_menu actions=row_actions />
         </PageFrame>
     }
 }
@@ -845,6 +901,32 @@ fn encode_q(s: &str) -> String {
         .unwrap_or_default()
 }
 
+/// URL потоковой CSV-выгрузки по текущим фильтрам и сортировке списка.
+fn export_url(st: &state::P903ListState) -> String {
+    let mut url = format!(
+        "{}/api/p903/finance-report/export?format=csv&date_from={}&date_to={}&sort_by={}&sort_desc={}",
+        api_base(),
+        encode_q(&st.date_from),
+        encode_q(&st.date_to),
+        encode_q(&st.sort_by),
+        !st.sort_ascending
+    );
+    if let Ok(nm) = st.nm_id_filter.trim().parse::<i64>() {
+        url.push_str(&format!("&nm_id={}", nm));
+    }
+    for (name, value) in [
+        ("sa_name", &st.sa_name_filter),
+        ("connection_mp_ref", &st.connection_filter),
+        ("supplier_oper_name", &st.operation_filter),
+        ("srid", &st.srid_filter),
+    ] {
+        if !value.trim().is_empty() {
+            url.push_str(&format!("&{}={}", name, encode_q(value.trim())));
+        }
+    }
+    url
+}
+
 async fn fetch_finance_report(
     limit: usize,
     offset: usize,
@@ -920,50 +1002,3 @@ async fn fetch_finance_report(
         serde_json::from_str(&text).map_err(|e| format!("{e}"))?;
     Ok(data)
 }
-
-/// Строит URL эндпоинта CSV-экспорта с учётом всех фильтров (без пагинации).
-fn build_export_url(
-    date_from: &str,
-    date_to: &str,
-    nm_id: &str,
-    sa_name: &str,
-    connection: &str,
-    operation: &str,
-    srid: &str,
-    sort_by: &str,
-    sort_desc: bool,
-) -> String {
-    let mut url = format!(
-        "/api/p903/finance-report/export?date_from={}&date_to={}&sort_by={}&sort_desc={}",
-        encode_q(date_from),
-        encode_q(date_to),
-        encode_q(sort_by),
-        if sort_desc { "true" } else { "false" }
-    );
-
-    if !nm_id.trim().is_empty() {
-        if let Ok(nm) = nm_id.parse::<i64>() {
-            url.push_str(&format!("&nm_id={}", nm));
-        }
-    }
-    if !sa_name.trim().is_empty() {
-        url.push_str(&format!("&sa_name={}", encode_q(sa_name.trim())));
-    }
-    if !connection.trim().is_empty() {
-        url.push_str(&format!(
-            "&connection_mp_ref={}",
-            encode_q(connection.trim())
-        ));
-    }
-    if !operation.trim().is_empty() {
-        url.push_str(&format!(
-            "&supplier_oper_name={}",
-            encode_q(operation.trim())
-        ));
-    }
-    if !srid.trim().is_empty() {
-        url.push_str(&format!("&srid={}", encode_q(srid.trim())));
-    }
-
-    url
-}
@@ -1,10 +1,12 @@
 use chrono::{Datelike, Utc};
-use contracts::projections::p904_sales_data::dto::SalesDataDto;
+use contracts::projections::p904_sales_data::dto::{SalesDataDto, SalesDataTotalsDto};
 use leptos::prelude::*;
 
 #[derive(Clone, Debug)]
 pub struct SalesDataState {
     pub sales: Vec<SalesDataDto>,
+    /// Итоги по `sales`, посчитанные на сервере
+    pub totals: SalesDataTotalsDto,
     pub date_from: String,
     pub date_to: String,
     pub cabinet_filter: String,
@@ -34,6 +36,7 @@ impl Default for SalesDataState {
 
         Self {
             sales: Vec::new(),
+            totals: SalesDataTotalsDto::default(),
             date_from: month_start.format("%Y-%m-%d").to_string(),
             date_to: month_end.format("%Y-%m-%d").to_string(),
             cabinet_filter: "".to_string(),
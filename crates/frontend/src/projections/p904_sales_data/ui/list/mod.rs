@@ -3,9 +3,10 @@ use crate::shared::components::date_input::DateInput;
 use crate::shared::components::month_selector::MonthSelector;
 use crate::shared::icons::icon;
 use crate::shared::list_utils::format_number;
+use crate::shared::registrator_link::{reg_tab_key, reg_type_name};
 use chrono::Datelike;
 use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
-use contracts::projections::p904_sales_data::dto::SalesDataDto;
+use contracts::projections::p904_sales_data::dto::{SalesDataDto, SalesDataListResponse};
 use leptos::logging::log;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
@@ -222,7 +223,8 @@ pub fn SalesDataList() -> impl IntoView {
             match fetch_sales(&query_params).await {
                 Ok(data) => {
                     let _ = state.try_update(|s| {
-                        s.sales = data;
+                        s.sales = data.items;
+                        s.totals = data.totals;
                         s.is_loaded = true;
                     });
                     set_loading.set(false);
@@ -337,8 +339,8 @@ pub fn SalesDataList() -> impl IntoView {
         });
     };
 
-    // Sorted sales data
-    let sorted_sales = move || {
+    // Sorted sales data (memoized: sorting thousands of rows on every render freezes the tab)
+    let sorted_sales = Memo::new(move |_| {
         let mut data = state.with(|s| s.sales.clone());
         let sort_col_opt = state.with(|s| s.sort_column.clone());
         let sort_asc = state.with(|s| s.sort_ascending);
@@ -425,43 +427,27 @@ pub fn SalesDataList() -> impl IntoView {
             }
         }
         data
-    };
+    });
 
-    // Calculate totals
+    // Totals come precomputed from the backend (SQL SUM over the same rows)
     let totals = move || {
-        let data = sorted_sales();
-        let count = data.len();
-        let customer_in: f64 = data.iter().map(|s| s.customer_in).sum();
-        let customer_out: f64 = data.iter().map(|s| s.customer_out).sum();
-        let coinvest_in: f64 = data.iter().map(|s| s.coinvest_in).sum();
-        let commission_out: f64 = data.iter().map(|s| s.commission_out).sum();
-        let acquiring_out: f64 = data.iter().map(|s| s.acquiring_out).sum();
-        let penalty_out: f64 = data.iter().map(|s| s.penalty_out).sum();
-        let logistics_out: f64 = data.iter().map(|s| s.logistics_out).sum();
-        let seller_out: f64 = data.iter().map(|s| s.seller_out).sum();
-        let price_full: f64 = data.iter().map(|s| s.price_full).sum();
-        let price_list: f64 = data.iter().map(|s| s.price_list).sum();
-        let price_return: f64 = data.iter().map(|s| s.price_return).sum();
-        let commission_percent: f64 = data.iter().map(|s| s.commission_percent).sum();
-        let coinvest_persent: f64 = data.iter().map(|s| s.coinvest_persent).sum();
-        let total: f64 = data.iter().map(|s| s.total).sum();
-
+        let t = state.with(|s| s.totals.clone());
         (
-            count,
-            customer_in,
-            customer_out,
-            coinvest_in,
-            commission_out,
-            acquiring_out,
-            penalty_out,
-            logistics_out,
-            seller_out,
-            price_full,
-            price_list,
-            price_return,
-            commission_percent,
-            coinvest_persent,
-            total,
+            t.count,
+            t.customer_in,
+            t.customer_out,
+            t.coinvest_in,
+            t.commission_out,
+            t.acquiring_out,
+            t.penalty_out,
+            t.logistics_out,
+            t.seller_out,
+            t.price_full,
+            t.price_list,
+            t.price_return,
+            t.commission_percent,
+            t.coinvest_persent,
+            t.total,
         )
     };
 
@@ -492,31 +478,25 @@ pub fn SalesDataList() -> impl IntoView {
         });
     };
 
-    let open_document =
-        move |registrator_type: String, registrator_ref: String, document_no: String| {
-            match registrator_type.as_str() {
-                "WB_Sales" => {
-                    tabs_store.open_tab(
-                        &format!("a012_wb_sales_details_{}", registrator_ref),
-                        &format!("WB Sales {}", document_no),
-                    );
-                }
-                "OZON_Transactions" => {
-                    tabs_store.open_tab(
-                        &format!("a014_ozon_transactions_details_{}", registrator_ref),
-                        &format!("OZON Txn {}", document_no),
-                    );
-                }
-                _ => {
-                    log!(
-                        "Unknown registrator type: {}, registrator_ref: {}, document_no: {}",
-                        registrator_type,
-                        registrator_ref,
-                        document_no
-                    );
-                }
+    let open_document = move |item: SalesDataDto| {
+        let tab = item.source_type.as_deref().and_then(|source_type| {
+            reg_tab_key(source_type, &item.registrator_ref)
+                .map(|key| (key, reg_type_name(source_type)))
+        });
+        match tab {
+            Some((tab_key, type_name)) => {
+                tabs_store.open_tab(&tab_key, &format!("{} {}", type_name, item.document_no));
             }
-        };
+            None => {
+                log!(
+                    "Unknown registrator type: {}, registrator_ref: {}, document_no: {}",
+                    item.registrator_type,
+                    item.registrator_ref,
+                    item.document_no
+                );
+            }
+        }
+    };
 
     // Load and restore settings from database
     let restore_settings = move |_| {
@@ -695,7 +675,7 @@ pub fn SalesDataList() -> impl IntoView {
 
                     <button
                         on:click=move |_| {
-                            let data = sorted_sales();
+                            let data = sorted_sales.get_untracked();
                             if let Err(e) = export_to_csv(&data) {
                                 log!("Failed to export: {}", e);
                             }
@@ -822,7 +802,7 @@ pub fn SalesDataList() -> impl IntoView {
                                     </tr>
                                 </thead>
                                 <tbody>
-                                    {sorted_sales().into_iter().map(|item| {
+                                    {sorted_sales.get().into_iter().map(|item| {
                                         let item_clone = item.clone();
                                         let open_doc = open_document.clone();
                                         // Format date to show only date part
@@ -839,7 +819,7 @@ pub fn SalesDataList() -> impl IntoView {
                                                         href="#"
                                                         on:click=move |ev| {
                                                             ev.prevent_default();
-                                                            open_doc(item_clone.registrator_type.clone(), item_clone.registrator_ref.clone(), item_clone.document_no.clone());
+                                                            open_doc(item_clone.clone());
                                                         }
                                                         style="color: #2196F3; text-decoration: underline; cursor: pointer;"
                                                     >
@@ -1050,7 +1030,7 @@ fn export_to_csv(data: &[SalesDataDto]) -> Result<(), String> {
     Ok(())
 }
 
-async fn fetch_sales(query_params: &str) -> Result<Vec<SalesDataDto>, String> {
+async fn fetch_sales(query_params: &str) -> Result<SalesDataListResponse, String> {
     use web_sys::{Request, RequestInit, RequestMode, Response};
 
     let opts = RequestInit::new();
@@ -1076,6 +1056,6 @@ async fn fetch_sales(query_params: &str) -> Result<Vec<SalesDataDto>, String> {
         .await
         .map_err(|e| format!("{e:?}"))?;
     let text: String = text.as_string().ok_or_else(|| "bad text".to_string())?;
-    let data: Vec<SalesDataDto> = serde_json::from_str(&text).map_err(|e| format!("{e}"))?;
+    let data: SalesDataListResponse = serde_json::from_str(&text).map_err(|e| format!("{e}"))?;
     Ok(data)
 }
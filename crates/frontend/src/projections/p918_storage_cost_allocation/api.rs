@@ -0,0 +1,139 @@
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::projections::p918_storage_cost_allocation::dto::{
+    StorageAllocationRebuildRequest, StorageAllocationRebuildResponse, StorageAllocationSettings,
+    StorageCostAllocationListResponse,
+};
+use gloo_net::http::{Request, Response};
+
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+/// Ошибки проверки правил (400) сервер объясняет текстом.
+async fn error_text(response: Response, action: &str) -> String {
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    if status == 400 && !text.is_empty() {
+        text
+    } else {
+        format!("Failed to {}: HTTP {}", action, status)
+    }
+}
+
+/// Доли расходов по SKU; `query` — готовая строка параметров без `?`.
+pub async fn fetch_allocations(query: &str) -> Result<StorageCostAllocationListResponse, String> {
+    let response = Request::get(&format!(
+        "{}/api/p918/storage-allocation?{}",
+        api_base(),
+        query
+    ))
+    .header("Authorization", &auth_header()?)
+    .header("Cache-Control", "no-cache")
+    .send()
+    .await
+    .map_err(|e| format!("Failed to fetch allocations: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "fetch allocations").await);
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse allocations: {}", e))
+}
+
+pub async fn fetch_settings() -> Result<StorageAllocationSettings, String> {
+    let response = Request::get(&format!(
+        "{}/api/p918/storage-allocation/settings",
+        api_base()
+    ))
+    .header("Authorization", &auth_header()?)
+    .send()
+    .await
+    .map_err(|e| format!("Failed to fetch allocation rules: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "fetch allocation rules").await);
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse allocation rules: {}", e))
+}
+
+pub async fn save_settings(
+    settings: &StorageAllocationSettings,
+) -> Result<StorageAllocationSettings, String> {
+    let response = Request::post(&format!(
+        "{}/api/p918/storage-allocation/settings",
+        api_base()
+    ))
+    .header("Authorization", &auth_header()?)
+    .json(settings)
+    .map_err(|e| format!("Failed to serialize allocation rules: {}", e))?
+    .send()
+    .await
+    .map_err(|e| format!("Failed to save allocation rules: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "save allocation rules").await);
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse allocation rules: {}", e))
+}
+
+/// Перераспределить расходы периода по текущим правилам.
+pub async fn rebuild(
+    request: &StorageAllocationRebuildRequest,
+) -> Result<StorageAllocationRebuildResponse, String> {
+    let response = Request::post(&format!(
+        "{}/api/p918/storage-allocation/rebuild",
+        api_base()
+    ))
+    .header("Authorization", &auth_header()?)
+    .json(request)
+    .map_err(|e| format!("Failed to serialize rebuild request: {}", e))?
+    .send()
+    .await
+    .map_err(|e| format!("Failed to rebuild allocations: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "rebuild allocations").await);
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse rebuild result: {}", e))
+}
+
+/// Кабинеты маркетплейсов — для правил по кабинету.
+pub async fn fetch_connections() -> Result<Vec<ConnectionMP>, String> {
+    let response = Request::get(&format!("{}/api/connection_mp", api_base()))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Ошибка загрузки кабинетов: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Ошибка загрузки кабинетов: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Ошибка разбора кабинетов: {}", e))
+}
@@ -0,0 +1,464 @@
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::common::AggregateId;
+use contracts::projections::p918_storage_cost_allocation::dto::{
+    default_driver, AllocationDriver, StorageAllocationRebuildRequest, StorageAllocationRule,
+    StorageAllocationSettings, StorageCostAllocationDto, ALLOCATED_TURNOVER_CODES, DRIVER_DIRECT,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::projections::p918_storage_cost_allocation::api;
+use crate::shared::icons::icon;
+use crate::shared::list_utils::format_number;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_LIST;
+
+/// Ключ вкладки «Распределение хранения по SKU».
+pub const STORAGE_ALLOCATION_TAB_KEY: &str = "p918_storage_cost_allocation";
+
+const PAGE_SIZE: u64 = 500;
+
+fn turnover_label(code: &str) -> &'static str {
+    match code {
+        "mp_storage" => "Хранение",
+        "acceptance" => "Приёмка",
+        _ => "Прочее",
+    }
+}
+
+fn driver_label(code: &str) -> String {
+    if code == DRIVER_DIRECT {
+        return "Товар из отчёта".to_string();
+    }
+    AllocationDriver::from_code(code)
+        .map(|d| d.label().to_string())
+        .unwrap_or_else(|| code.to_string())
+}
+
+fn month_start() -> String {
+    let today = chrono::Utc::now().date_naive();
+    today.format("%Y-%m-01").to_string()
+}
+
+fn today() -> String {
+    chrono::Utc::now()
+        .date_naive()
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+#[component]
+pub fn StorageCostAllocationPage() -> impl IntoView {
+    let connections = RwSignal::<Vec<ConnectionMP>>::new(Vec::new());
+    let rules = RwSignal::<Vec<StorageAllocationRule>>::new(Vec::new());
+    let items = RwSignal::<Vec<StorageCostAllocationDto>>::new(Vec::new());
+    let total_count = RwSignal::new(0u64);
+    let loading = RwSignal::new(false);
+    let busy = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let notice = RwSignal::<Option<String>>::new(None);
+
+    let date_from = RwSignal::new(month_start());
+    let date_to = RwSignal::new(today());
+    let turnover_code = RwSignal::new(String::new());
+    let nm_id = RwSignal::new(String::new());
+    let unallocated_only = RwSignal::new(false);
+
+    let connection_name = move |id: &str| {
+        connections.with(|list| {
+            list.iter()
+                .find(|c| c.base.id.as_string() == id)
+                .map(|c| c.base.description.clone())
+                .unwrap_or_else(|| id.to_string())
+        })
+    };
+
+    let reload = Callback::new(move |_| {
+        let mut params = vec![
+            format!("limit={}", PAGE_SIZE),
+            format!("date_from={}", date_from.get_untracked()),
+            format!("date_to={}", date_to.get_untracked()),
+        ];
+        let code = turnover_code.get_untracked();
+        if !code.is_empty() {
+            params.push(format!("turnover_code={}", code));
+        }
+        if let Ok(value) = nm_id.get_untracked().trim().parse::<i64>() {
+            params.push(format!("nm_id={}", value));
+        }
+        if unallocated_only.get_untracked() {
+            params.push("unallocated_only=true".to_string());
+        }
+        let query = params.join("&");
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_allocations(&query).await {
+                Ok(response) => {
+                    total_count.set(response.total_count);
+                    items.set(response.items);
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+        spawn_local(async move {
+            if let Ok(list) = api::fetch_connections().await {
+                connections.set(list);
+            }
+            match api::fetch_settings().await {
+                Ok(settings) => rules.set(settings.rules),
+                Err(err) => error.set(Some(err)),
+            }
+        });
+    });
+
+    let add_rule = move |_| {
+        rules.update(|list| {
+            list.push(StorageAllocationRule {
+                turnover_code: ALLOCATED_TURNOVER_CODES[0].to_string(),
+                connection_mp_ref: None,
+                driver: default_driver(ALLOCATED_TURNOVER_CODES[0]),
+            })
+        });
+    };
+
+    let save_rules = move |_| {
+        let settings = StorageAllocationSettings {
+            rules: rules.get_untracked(),
+        };
+        if let Err(err) = settings.validate() {
+            error.set(Some(err));
+            return;
+        }
+        busy.set(true);
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::save_settings(&settings).await {
+                Ok(saved) => {
+                    rules.set(saved.rules);
+                    notice.set(Some(
+                        "Правила сохранены. Они действуют на следующие проведения; чтобы пересчитать период, нажмите «Перераспределить»."
+                            .to_string(),
+                    ));
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let rebuild = move |_| {
+        let request = StorageAllocationRebuildRequest {
+            date_from: date_from.get_untracked(),
+            date_to: date_to.get_untracked(),
+        };
+        busy.set(true);
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::rebuild(&request).await {
+                Ok(result) => {
+                    notice.set(Some(format!(
+                        "Перераспределено строк расхода: {}, без данных драйвера: {}.",
+                        result.source_rows, result.unallocated_rows
+                    )));
+                    reload.run(());
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let update_rule = move |index: usize, change: Box<dyn FnOnce(&mut StorageAllocationRule)>| {
+        rules.update(|list| {
+            if let Some(rule) = list.get_mut(index) {
+                change(rule);
+            }
+        });
+    };
+
+    let rules_table = move || {
+        rules
+            .get()
+            .into_iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                let code = rule.turnover_code.clone();
+                let connection = rule.connection_mp_ref.clone().unwrap_or_default();
+                let driver = rule.driver.code();
+                view! {
+                    <tr class="table__row">
+                        <td class="table__cell">
+                            <select
+                                class="thaw-input"
+                                prop:value=code
+                                on:change=move |ev| {
+                                    let value = event_target_value(&ev);
+                                    update_rule(index, Box::new(move |r| r.turnover_code = value));
+                                }
+                            >
+                                {ALLOCATED_TURNOVER_CODES
+                                    .iter()
+                                    .map(|c| view! { <option value=*c>{turnover_label(c)}</option> })
+                                    .collect_view()}
+                            </select>
+                        </td>
+                        <td class="table__cell">
+                            <select
+                                class="thaw-input"
+                                prop:value=connection
+                                on:change=move |ev| {
+                                    let value = event_target_value(&ev);
+                                    update_rule(
+                                        index,
+                                        Box::new(move |r| r.connection_mp_ref = (!value.is_empty()).then_some(value)),
+                                    );
+                                }
+                            >
+                                <option value="">"Все кабинеты"</option>
+                                {move || {
+                                    connections
+                                        .get()
+                                        .into_iter()
+                                        .map(|c| {
+                                            let id = c.base.id.as_string();
+                                            view! { <option value=id>{c.base.description}</option> }
+                                        })
+                                        .collect_view()
+                                }}
+                            </select>
+                        </td>
+                        <td class="table__cell">
+                            <select
+                                class="thaw-input"
+                                prop:value=driver
+                                on:change=move |ev| {
+                                    if let Some(value) = AllocationDriver::from_code(&event_target_value(&ev)) {
+                                        update_rule(index, Box::new(move |r| r.driver = value));
+                                    }
+                                }
+                            >
+                                {AllocationDriver::ALL
+                                    .into_iter()
+                                    .map(|d| view! { <option value=d.code()>{d.label()}</option> })
+                                    .collect_view()}
+                            </select>
+                        </td>
+                        <td class="table__cell">
+                            <button
+                                class="button button--secondary"
+                                title="Удалить правило"
+                                on:click=move |_| rules.update(|list| {
+                                    if index < list.len() {
+                                        list.remove(index);
+                                    }
+                                })
+                            >
+                                {icon("trash")}
+                            </button>
+                        </td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <PageFrame page_id="p918_storage_cost_allocation--list" category=PAGE_CAT_LIST class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Распределение хранения по SKU"</h1>
+                    <p class="page__subtitle">
+                        "Хранение и платная приёмка WB без товара делятся между SKU по драйверу и попадают в маржу товара (D409). По умолчанию хранение — по доле объёма, приёмка — по штукам на хранении."
+                    </p>
+                </div>
+                <div class="page__header-right">
+                    <button
+                        class="button button--secondary"
+                        disabled=move || loading.get()
+                        on:click=move |_| reload.run(())
+                    >
+                        {icon("refresh-cw")}
+                        {move || if loading.get() { "Обновление данных..." } else { "Обновить данные" }}
+                    </button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+                {move || notice.get().map(|text| view! {
+                    <div class="alert alert--success">{text}</div>
+                })}
+
+                <div class="card" style="padding: var(--spacing-md); margin-bottom: var(--spacing-md);">
+                    <h4 class="details-section__title">"Правила распределения"</h4>
+                    <p class="page__subtitle">"Правило кабинета важнее общего; без правила действует драйвер по умолчанию."</p>
+                    <table class="table__data table--striped">
+                        <thead class="table__head">
+                            <tr>
+                                <th class="table__header-cell">"Расход"</th>
+                                <th class="table__header-cell">"Кабинет"</th>
+                                <th class="table__header-cell">"Драйвер"</th>
+                                <th class="table__header-cell"></th>
+                            </tr>
+                        </thead>
+                        <tbody>{rules_table}</tbody>
+                    </table>
+                    <div style="display: flex; gap: var(--spacing-sm); margin-top: var(--spacing-sm);">
+                        <button class="button button--secondary" on:click=add_rule>
+                            {icon("plus")} "Добавить правило"
+                        </button>
+                        <button class="button button--primary" disabled=move || busy.get() on:click=save_rules>
+                            {icon("save")} "Сохранить правила"
+                        </button>
+                    </div>
+                </div>
+
+                <div style="display: flex; flex-wrap: wrap; gap: var(--spacing-md); align-items: flex-end; margin-bottom: var(--spacing-md);">
+                    <div class="form__group">
+                        <label class="form__label">"Дата с"</label>
+                        <input
+                            type="date"
+                            class="form__input"
+                            prop:value=move || date_from.get()
+                            on:input=move |ev| date_from.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="form__group">
+                        <label class="form__label">"Дата по"</label>
+                        <input
+                            type="date"
+                            class="form__input"
+                            prop:value=move || date_to.get()
+                            on:input=move |ev| date_to.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="form__group">
+                        <label class="form__label">"Расход"</label>
+                        <select
+                            class="thaw-input"
+                            prop:value=move || turnover_code.get()
+                            on:change=move |ev| turnover_code.set(event_target_value(&ev))
+                        >
+                            <option value="">"Все"</option>
+                            {ALLOCATED_TURNOVER_CODES
+                                .iter()
+                                .map(|c| view! { <option value=*c>{turnover_label(c)}</option> })
+                                .collect_view()}
+                        </select>
+                    </div>
+                    <div class="form__group">
+                        <label class="form__label">"nmID"</label>
+                        <input
+                            type="text"
+                            class="form__input"
+                            prop:value=move || nm_id.get()
+                            on:input=move |ev| nm_id.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <label class="form__group" style="display: flex; gap: var(--spacing-xs); align-items: center;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || unallocated_only.get()
+                            on:change=move |ev| unallocated_only.set(event_target_checked(&ev))
+                        />
+                        <span>"Только нераспределённые"</span>
+                    </label>
+                    <button class="button button--primary" on:click=move |_| reload.run(())>
+                        {icon("search")} "Найти"
+                    </button>
+                    <button
+                        class="button button--secondary"
+                        title="Пересчитать доли периода по текущим правилам и остаткам"
+                        disabled=move || busy.get()
+                        on:click=rebuild
+                    >
+                        {icon("refresh")} "Перераспределить"
+                    </button>
+                </div>
+
+                <p class="page__subtitle">
+                    {move || format!("Строк: {}", format_number(total_count.get() as f64))}
+                </p>
+                <div class="table-wrapper">
+                    <table class="table__data table--striped">
+                        <thead class="table__head">
+                            <tr>
+                                <th class="table__header-cell">"Дата"</th>
+                                <th class="table__header-cell">"Расход"</th>
+                                <th class="table__header-cell">"Кабинет"</th>
+                                <th class="table__header-cell table__header-cell--right">"Сумма строки"</th>
+                                <th class="table__header-cell">"Драйвер"</th>
+                                <th class="table__header-cell">"База"</th>
+                                <th class="table__header-cell">"nmID"</th>
+                                <th class="table__header-cell">"Артикул"</th>
+                                <th class="table__header-cell table__header-cell--right">"Значение / итог"</th>
+                                <th class="table__header-cell table__header-cell--right">"Доля, %"</th>
+                                <th class="table__header-cell table__header-cell--right">"На SKU"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <Show when=move || !loading.get() && items.with(|list| list.is_empty())>
+                                <tr class="table__row">
+                                    <td class="table__cell" colspan="11">"Нет распределённых расходов за период"</td>
+                                </tr>
+                            </Show>
+                            {move || {
+                                items
+                                    .get()
+                                    .into_iter()
+                                    .map(|row| {
+                                        let unallocated = row.nm_id.is_none();
+                                        let nm = row
+                                            .nm_id
+                                            .map(|id| id.to_string())
+                                            .unwrap_or_else(|| "не распределено".to_string());
+                                        let driver_value = if row.driver_total > 0.0 {
+                                            format!(
+                                                "{} / {}",
+                                                format_number(row.driver_value),
+                                                format_number(row.driver_total)
+                                            )
+                                        } else {
+                                            String::new()
+                                        };
+                                        view! {
+                                            <tr class="table__row" title=row.source_ref.clone()>
+                                                <td class="table__cell">{row.entry_date.clone()}</td>
+                                                <td class="table__cell">{turnover_label(&row.turnover_code)}</td>
+                                                <td class="table__cell">{connection_name(&row.connection_mp_ref)}</td>
+                                                <td class="table__cell table__cell--right">{format!("{:.2}", row.source_amount)}</td>
+                                                <td class="table__cell">{driver_label(&row.driver)}</td>
+                                                <td class="table__cell">
+                                                    {row.basis.clone()}
+                                                    {row.comment.clone().map(|comment| view! {
+                                                        " "
+                                                        <span class="badge badge--warning">{comment}</span>
+                                                    })}
+                                                </td>
+                                                <td class="table__cell">{if unallocated { view! { <span class="badge badge--warning">{nm}</span> }.into_any() } else { view! { <span>{nm}</span> }.into_any() }}</td>
+                                                <td class="table__cell">{row.article.clone()}</td>
+                                                <td class="table__cell table__cell--right">{driver_value}</td>
+                                                <td class="table__cell table__cell--right">{format!("{:.2}", row.share * 100.0)}</td>
+                                                <td class="table__cell table__cell--right">{format!("{:.2}", row.amount)}</td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+            </div>
+        </PageFrame>
+    }
+}
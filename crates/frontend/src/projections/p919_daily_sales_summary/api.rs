@@ -0,0 +1,29 @@
+use contracts::projections::p919_daily_sales_summary::dto::{
+    DailySalesSummaryRebuildResponse, DailySalesSummaryResponse,
+};
+
+use crate::shared::api_client;
+
+/// Итоги по дням; пустые фильтры не передаются — сервер берёт последние 90 дней.
+pub async fn fetch_summary(
+    date_from: &str,
+    date_to: &str,
+    marketplace: &str,
+    organization_ref: &str,
+) -> Result<DailySalesSummaryResponse, String> {
+    let params: Vec<String> = [
+        ("date_from", date_from),
+        ("date_to", date_to),
+        ("marketplace", marketplace),
+        ("organization_ref", organization_ref),
+    ]
+    .into_iter()
+    .filter(|(_, value)| !value.trim().is_empty())
+    .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value.trim())))
+    .collect();
+    Ok(api_client::get_json(&format!("/api/p919/daily-summary?{}", params.join("&"))).await?)
+}
+
+pub async fn rebuild() -> Result<DailySalesSummaryRebuildResponse, String> {
+    Ok(api_client::post_json("/api/p919/daily-summary/rebuild", &()).await?)
+}
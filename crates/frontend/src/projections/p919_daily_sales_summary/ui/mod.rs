@@ -0,0 +1,487 @@
+//! Дневная сводка продаж (p919): график выбранного показателя по дням за период
+//! (по умолчанию последние 90 дней), итоги периода и таблица по дням.
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate, Utc};
+use contracts::domain::a002_organization::aggregate::Organization;
+use contracts::domain::common::AggregateId;
+use contracts::projections::p919_daily_sales_summary::dto::{
+    DailySalesSummaryDto, DailySalesSummaryResponse, DEFAULT_PERIOD_DAYS,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::projections::p919_daily_sales_summary::api;
+use crate::shared::api_client;
+use crate::shared::money_format::{format_money, format_number};
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_DASHBOARD;
+
+/// Ключ вкладки «Дневная сводка продаж».
+pub const DAILY_SALES_SUMMARY_TAB_KEY: &str = "p919_daily_sales_summary";
+
+/// Показатель, который рисует график.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Metric {
+    Gross,
+    Net,
+    Commission,
+    Orders,
+    Units,
+}
+
+impl Metric {
+    const ALL: [Metric; 5] = [
+        Metric::Gross,
+        Metric::Net,
+        Metric::Commission,
+        Metric::Orders,
+        Metric::Units,
+    ];
+
+    fn code(self) -> &'static str {
+        match self {
+            Metric::Gross => "gross",
+            Metric::Net => "net",
+            Metric::Commission => "commission",
+            Metric::Orders => "orders",
+            Metric::Units => "units",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Metric::Gross => "Выручка",
+            Metric::Net => "Итог",
+            Metric::Commission => "Комиссия",
+            Metric::Orders => "Заказы",
+            Metric::Units => "Штуки",
+        }
+    }
+
+    fn from_code(code: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|m| m.code() == code)
+            .unwrap_or(Metric::Gross)
+    }
+
+    fn value(self, day: &DayTotals) -> f64 {
+        match self {
+            Metric::Gross => day.gross,
+            Metric::Net => day.net,
+            Metric::Commission => day.commission,
+            Metric::Orders => day.orders_count as f64,
+            Metric::Units => day.units,
+        }
+    }
+
+    fn format(self, value: f64) -> String {
+        match self {
+            Metric::Orders | Metric::Units => format_number(value, 0),
+            _ => format_money(value),
+        }
+    }
+}
+
+/// Итоги дня по всем строкам сводки, прошедшим фильтр.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct DayTotals {
+    date: String,
+    orders_count: i64,
+    units: f64,
+    gross: f64,
+    commission: f64,
+    net: f64,
+}
+
+impl DayTotals {
+    fn add(&mut self, row: &DailySalesSummaryDto) {
+        self.orders_count += row.orders_count;
+        self.units += row.units;
+        self.gross += row.gross;
+        self.commission += row.commission;
+        self.net += row.net;
+    }
+}
+
+/// Все дни периода подряд, включая дни без продаж, — чтобы ось X была равномерной.
+fn days_of_period(response: &DailySalesSummaryResponse) -> Vec<DayTotals> {
+    let mut by_date: BTreeMap<String, DayTotals> = BTreeMap::new();
+    if let (Ok(from), Ok(to)) = (
+        NaiveDate::parse_from_str(&response.date_from, "%Y-%m-%d"),
+        NaiveDate::parse_from_str(&response.date_to, "%Y-%m-%d"),
+    ) {
+        let mut day = from;
+        while day <= to {
+            let date = day.format("%Y-%m-%d").to_string();
+            by_date.insert(
+                date.clone(),
+                DayTotals {
+                    date,
+                    ..Default::default()
+                },
+            );
+            day += Duration::days(1);
+        }
+    }
+    for row in &response.items {
+        by_date
+            .entry(row.date.clone())
+            .or_insert_with(|| DayTotals {
+                date: row.date.clone(),
+                ..Default::default()
+            })
+            .add(row);
+    }
+    by_date.into_values().collect()
+}
+
+fn period_totals(days: &[DayTotals]) -> DayTotals {
+    days.iter().fold(DayTotals::default(), |mut acc, day| {
+        acc.orders_count += day.orders_count;
+        acc.units += day.units;
+        acc.gross += day.gross;
+        acc.commission += day.commission;
+        acc.net += day.net;
+        acc
+    })
+}
+
+fn default_date_from() -> String {
+    (Utc::now().date_naive() - Duration::days(DEFAULT_PERIOD_DAYS - 1))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn today() -> String {
+    Utc::now().date_naive().format("%Y-%m-%d").to_string()
+}
+
+/// Столбцы по дням; отрицательные значения (возвраты, комиссия) уходят вниз от нуля.
+fn daily_chart(days: &[DayTotals], metric: Metric) -> impl IntoView {
+    let vb_w = 900.0_f64;
+    let vb_h = 260.0_f64;
+    let pad_left = 64.0;
+    let pad_right = 12.0;
+    let pad_top = 12.0;
+    let pad_bottom = 28.0;
+    let plot_w = vb_w - pad_left - pad_right;
+    let plot_h = vb_h - pad_top - pad_bottom;
+
+    let values: Vec<f64> = days.iter().map(|day| metric.value(day)).collect();
+    let max_value = values.iter().fold(0.0_f64, |acc, v| acc.max(*v));
+    let min_value = values.iter().fold(0.0_f64, |acc, v| acc.min(*v));
+    let range = (max_value - min_value).max(1.0);
+    let y = move |value: f64| pad_top + plot_h * (max_value - value) / range;
+    let slot = plot_w / days.len().max(1) as f64;
+    let bar_w = (slot * 0.8).max(1.0);
+
+    let grid = (0..=4)
+        .map(|step| {
+            let value = min_value + range * step as f64 / 4.0;
+            let gy = y(value);
+            view! {
+                <line class="p919-grid" x1=pad_left x2=vb_w - pad_right y1=gy y2=gy />
+                <text class="p919-axis" x=pad_left - 6.0 y=gy text-anchor="end" dominant-baseline="central">
+                    {format_number(value, 0)}
+                </text>
+            }
+        })
+        .collect_view();
+    let zero_y = y(0.0);
+    let bars = days
+        .iter()
+        .zip(values.iter())
+        .enumerate()
+        .map(|(index, (day, value))| {
+            let x = pad_left + slot * index as f64 + (slot - bar_w) / 2.0;
+            let top = y(value.max(0.0));
+            let height = (y(value.min(0.0)) - top).max(0.5);
+            let class = if *value < 0.0 {
+                "p919-bar p919-bar--negative"
+            } else {
+                "p919-bar"
+            };
+            let title = format!("{} · {}", day.date, metric.format(*value));
+            view! {
+                <rect class=class x=x y=top width=bar_w height=height>
+                    <title>{title}</title>
+                </rect>
+            }
+        })
+        .collect_view();
+    // Подписи дат — не чаще, чем помещаются.
+    let label_step = (days.len() / 12).max(1);
+    let labels = days
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| index % label_step == 0)
+        .map(|(index, day)| {
+            let label = day.date.get(5..).unwrap_or(&day.date).to_string();
+            view! {
+                <text class="p919-axis" x=pad_left + slot * (index as f64 + 0.5) y=vb_h - 8.0 text-anchor="middle">
+                    {label}
+                </text>
+            }
+        })
+        .collect_view();
+
+    let view_box = format!("0 0 {vb_w} {vb_h}");
+    let aria_label = format!("{} по дням", metric.label());
+    view! {
+        <svg class="p919-chart" viewBox=view_box preserveAspectRatio="none" role="img" aria-label=aria_label>
+            {grid}
+            <line class="p919-zero" x1=pad_left x2=vb_w - pad_right y1=zero_y y2=zero_y />
+            {bars}
+            {labels}
+        </svg>
+    }
+}
+
+fn totals_cards(totals: &DayTotals) -> impl IntoView {
+    let cards = [
+        ("Заказы", format_number(totals.orders_count as f64, 0)),
+        ("Штуки", format_number(totals.units, 0)),
+        ("Выручка", format_money(totals.gross)),
+        ("Комиссия", format_money(totals.commission)),
+        ("Итог", format_money(totals.net)),
+    ];
+    view! {
+        <div class="p919-cards">
+            {cards
+                .into_iter()
+                .map(|(label, value)| view! {
+                    <div class="p919-card">
+                        <div class="p919-card-label">{label}</div>
+                        <div class="p919-card-value">{value}</div>
+                    </div>
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn DailySalesSummaryPage() -> impl IntoView {
+    let date_from = RwSignal::new(default_date_from());
+    let date_to = RwSignal::new(today());
+    let marketplace = RwSignal::new(String::new());
+    let organization_ref = RwSignal::new(String::new());
+    let metric = RwSignal::new(Metric::Gross);
+    let organizations = RwSignal::new(Vec::<(String, String)>::new());
+    let data = RwSignal::new(None::<DailySalesSummaryResponse>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+    let notice = RwSignal::new(None::<String>);
+
+    spawn_local(async move {
+        if let Ok(list) = api_client::get_json::<Vec<Organization>>("/api/organization").await {
+            let mut opts: Vec<(String, String)> = list
+                .into_iter()
+                .map(|org| (org.base.id.as_string(), org.base.description))
+                .collect();
+            opts.sort_by(|a, b| a.1.cmp(&b.1));
+            organizations.set(opts);
+        }
+    });
+
+    let load = move || {
+        let df = date_from.get_untracked();
+        let dt = date_to.get_untracked();
+        let mp = marketplace.get_untracked();
+        let org = organization_ref.get_untracked();
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_summary(&df, &dt, &mp, &org).await {
+                Ok(response) => data.set(Some(response)),
+                Err(message) => error.set(Some(message)),
+            }
+            loading.set(false);
+        });
+    };
+
+    let rebuild = move |_| {
+        loading.set(true);
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::rebuild().await {
+                Ok(result) => {
+                    notice.set(Some(format!(
+                        "Сводка пересобрана: документов {}, строк {}",
+                        result.documents, result.rows
+                    )));
+                    load();
+                }
+                Err(message) => {
+                    error.set(Some(message));
+                    loading.set(false);
+                }
+            }
+        });
+    };
+
+    Effect::new(move |_| load());
+
+    view! {
+        <PageFrame page_id="p919_daily_sales_summary--dashboard" category=PAGE_CAT_DASHBOARD class="page--wide">
+            <style>
+                ".p919-shell{display:flex;flex-direction:column;gap:12px;height:100%}
+                .p919-toolbar{display:flex;gap:10px;align-items:end;flex-wrap:wrap;padding:10px 0}
+                .p919-field{display:flex;flex-direction:column;gap:4px;min-width:140px}
+                .p919-field label{font-size:12px;color:var(--color-text-secondary)}
+                .p919-field input,.p919-field select{height:32px;border:1px solid var(--color-border);border-radius:6px;padding:0 8px;background:var(--color-surface);color:var(--color-text-primary)}
+                .p919-btn{height:32px;border:1px solid var(--color-border);border-radius:6px;background:var(--color-surface);color:var(--color-text-primary);padding:0 12px;cursor:pointer}
+                .p919-cards{display:grid;grid-template-columns:repeat(auto-fit,minmax(150px,1fr));gap:10px}
+                .p919-card{border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface);padding:10px 12px}
+                .p919-card-label{font-size:12px;color:var(--color-text-secondary)}
+                .p919-card-value{font-size:20px;font-weight:600;font-variant-numeric:tabular-nums}
+                .p919-chart-wrap{border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface);padding:8px}
+                .p919-chart{width:100%;height:260px;display:block}
+                .p919-grid{stroke:var(--color-border-light,var(--color-border));stroke-width:1}
+                .p919-zero{stroke:var(--color-border);stroke-width:1}
+                .p919-axis{font-size:10px;fill:var(--color-text-secondary)}
+                .p919-bar{fill:#2563eb;opacity:0.88}
+                .p919-bar--negative{fill:#dc2626}
+                .p919-table-wrap{overflow:auto;border:1px solid var(--color-border-light,var(--color-border));border-radius:8px;background:var(--color-surface)}
+                .p919-table{width:100%;border-collapse:collapse;font-size:12px}
+                .p919-table th{position:sticky;top:0;background:var(--color-surface);z-index:1;border-bottom:1px solid var(--color-border);padding:6px 8px;color:var(--color-text-secondary);font-weight:600;white-space:nowrap;text-align:left}
+                .p919-table td{border-bottom:1px solid var(--color-border-light,var(--color-border));padding:6px 8px;white-space:nowrap}
+                .p919-num{text-align:right !important;font-variant-numeric:tabular-nums}
+                .p919-state{padding:18px;color:var(--color-text-secondary)}"
+            </style>
+            <div class="p919-shell">
+                <div>
+                    <h1 style="margin:0;font-size:20px;">"Дневная сводка продаж"</h1>
+                    <div style="color:var(--color-text-secondary);font-size:13px;">
+                        "Проведённые продажи WB и транзакции OZON по дням: заказы, штуки, выручка, комиссия и итог (p919)"
+                    </div>
+                </div>
+
+                <div class="p919-toolbar">
+                    <div class="p919-field">
+                        <label>"С"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_from.get()
+                            on:input=move |ev| date_from.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="p919-field">
+                        <label>"по"</label>
+                        <input
+                            type="date"
+                            prop:value=move || date_to.get()
+                            on:input=move |ev| date_to.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <div class="p919-field">
+                        <label>"Маркетплейс"</label>
+                        <select
+                            prop:value=move || marketplace.get()
+                            on:change=move |ev| marketplace.set(event_target_value(&ev))
+                        >
+                            <option value="">"Все"</option>
+                            <option value="WB">"Wildberries"</option>
+                            <option value="OZON">"OZON"</option>
+                        </select>
+                    </div>
+                    <div class="p919-field">
+                        <label>"Организация"</label>
+                        <select
+                            prop:value=move || organization_ref.get()
+                            on:change=move |ev| organization_ref.set(event_target_value(&ev))
+                        >
+                            <option value="">"Все организации"</option>
+                            <For
+                                each=move || organizations.get()
+                                key=|(id, _)| id.clone()
+                                children=move |(id, label)| {
+                                    view! { <option value=id.clone()>{label}</option> }
+                                }
+                            />
+                        </select>
+                    </div>
+                    <div class="p919-field">
+                        <label>"Показатель"</label>
+                        <select
+                            prop:value=move || metric.get().code()
+                            on:change=move |ev| metric.set(Metric::from_code(&event_target_value(&ev)))
+                        >
+                            {Metric::ALL
+                                .into_iter()
+                                .map(|m| view! { <option value=m.code()>{m.label()}</option> })
+                                .collect_view()}
+                        </select>
+                    </div>
+                    <button class="p919-btn" on:click=move |_| load() disabled=move || loading.get()>
+                        {move || if loading.get() { "Загрузка..." } else { "Обновить" }}
+                    </button>
+                    <button
+                        class="p919-btn"
+                        title="Пересчитать сводку по всем проведённым документам"
+                        on:click=rebuild
+                        disabled=move || loading.get()
+                    >
+                        "Пересобрать"
+                    </button>
+                </div>
+
+                {move || error.get().map(|message| view! {
+                    <div class="alert alert--error">{message}</div>
+                })}
+                {move || notice.get().map(|message| view! {
+                    <div class="alert alert--success">{message}</div>
+                })}
+
+                {move || data.get().map(|response| {
+                    if response.items.is_empty() {
+                        return view! {
+                            <div class="p919-state">
+                                "За период нет проведённых продаж. Если документы уже проведены до появления сводки, нажмите «Пересобрать»."
+                            </div>
+                        }.into_any();
+                    }
+                    let days = days_of_period(&response);
+                    let totals = period_totals(&days);
+                    let selected = metric.get();
+                    view! {
+                        {totals_cards(&totals)}
+                        <div class="p919-chart-wrap">
+                            {daily_chart(&days, selected)}
+                        </div>
+                        <div class="p919-table-wrap">
+                            <table class="p919-table">
+                                <thead>
+                                    <tr>
+                                        <th>"Дата"</th>
+                                        <th class="p919-num">"Заказы"</th>
+                                        <th class="p919-num">"Штуки"</th>
+                                        <th class="p919-num">"Выручка"</th>
+                                        <th class="p919-num">"Комиссия"</th>
+                                        <th class="p919-num">"Итог"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {days.into_iter().rev().map(|day| view! {
+                                        <tr>
+                                            <td>{day.date}</td>
+                                            <td class="p919-num">{day.orders_count}</td>
+                                            <td class="p919-num">{format_number(day.units, 0)}</td>
+                                            <td class="p919-num">{format_money(day.gross)}</td>
+                                            <td class="p919-num">{format_money(day.commission)}</td>
+                                            <td class="p919-num">{format_money(day.net)}</td>
+                                        </tr>
+                                    }).collect_view()}
+                                </tbody>
+                            </table>
+                        </div>
+                    }.into_any()
+                })}
+            </div>
+        </PageFrame>
+    }
+}
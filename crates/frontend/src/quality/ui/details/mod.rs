@@ -9,6 +9,7 @@
 //! - таблицу метрик с долей соответствия по каждому источнику + drill-down;
 //! - разрезы метрик (по кабинету, по исправимости и т.п.);
 //! - примеры нарушений целостности GL с переходом в карточку GL;
+//! - принятие расхождений схемы API (`schema_drift`) с перезапуском проверки;
 //! - drill-down по регистраторам (группы → строки → перепроведение/очистка).
 
 use crate::layout::global_context::AppGlobalContext;
@@ -16,7 +17,7 @@ use crate::quality::ui::drilldown::{ProjectionRowsPanel, RegistratorGroupsPanel}
 use crate::shared::api_utils::api_base;
 use crate::shared::icons::icon;
 use crate::shared::page_frame::PageFrame;
-use contracts::quality::{CheckBreakdown, CheckDetails};
+use contracts::quality::{CheckBreakdown, CheckDetails, ViolationAcknowledgeRequest};
 use gloo_net::http::Request;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
@@ -137,6 +138,45 @@ fn build_llm_prompt(details: &CheckDetails) -> String {
     out
 }
 
+/// Нарушения, которые принимаются кнопкой (расхождения схемы API маркетплейсов).
+fn is_acknowledgeable(violation_type: &str) -> bool {
+    matches!(
+        violation_type,
+        "unknown_field" | "missing_field" | "row_rejected"
+    )
+}
+
+/// Принимает нарушения проверки и перезапускает её.
+fn acknowledge_violations(
+    check_id: String,
+    ids: Vec<String>,
+    set_reload: WriteSignal<u32>,
+    set_ack_error: WriteSignal<Option<String>>,
+) {
+    spawn_local(async move {
+        let url = format!("{}/api/quality/checks/{}/acknowledge", api_base(), check_id);
+        let body = serde_json::to_string(&ViolationAcknowledgeRequest { ids }).unwrap_or_default();
+        let response = match Request::post(&url)
+            .header("Content-Type", "application/json")
+            .body(body)
+        {
+            Ok(request) => request.send().await,
+            Err(e) => {
+                set_ack_error.set(Some(format!("Ошибка запроса: {e}")));
+                return;
+            }
+        };
+        match response {
+            Ok(resp) if resp.status() == 200 => {
+                set_ack_error.set(None);
+                set_reload.update(|v| *v += 1);
+            }
+            Ok(resp) => set_ack_error.set(Some(format!("HTTP {}", resp.status()))),
+            Err(e) => set_ack_error.set(Some(format!("Ошибка запроса: {e}"))),
+        }
+    });
+}
+
 /// Что показано в нижней панели drill-down.
 #[derive(Debug, Clone, PartialEq)]
 enum Drill {
@@ -167,6 +207,7 @@ pub fn QualityCheckDetails(check_id: String) -> impl IntoView {
     let (error, set_error) = signal::<Option<String>>(None);
     let (drill, set_drill) = signal(Drill::None);
     let (reload, set_reload) = signal(0u32);
+    let (ack_error, set_ack_error) = signal::<Option<String>>(None);
 
     let cid_for_fetch = check_id.clone();
     Effect::new(move |_| {
@@ -236,6 +277,7 @@ pub fn QualityCheckDetails(check_id: String) -> impl IntoView {
                 let cid_metrics = cid_page.clone();
                 let cid_groups = cid_page.clone();
                 let cid_rows = cid_page.clone();
+                let cid_ack = cid_page.clone();
 
                 view! {
                     <div class="page__header">
@@ -357,8 +399,27 @@ pub fn QualityCheckDetails(check_id: String) -> impl IntoView {
                         // --- примеры нарушений (GL) ---
                         {if !result.violations.is_empty() {
                             let violations = result.violations.clone();
+                            let ack_ids: Vec<String> = violations
+                                .iter()
+                                .filter(|v| is_acknowledgeable(&v.violation_type))
+                                .filter_map(|v| v.projection_id.clone())
+                                .collect();
+                            let cid_ack_all = cid_ack.clone();
                             view! {
                                 <h3 style="margin: 16px 0 6px; font-size: 0.95rem;">{format!("Примеры нарушений — {} шт.", violations.len())}</h3>
+                                {(!ack_ids.is_empty()).then(|| {
+                                    let ids = ack_ids.clone();
+                                    let cid = cid_ack_all.clone();
+                                    view! {
+                                        <div style="display: flex; gap: 8px; align-items: center; margin-bottom: 6px;">
+                                            <thaw::Button
+                                                size=thaw::ButtonSize::Small
+                                                on_click=move |_| acknowledge_violations(cid.clone(), ids.clone(), set_reload, set_ack_error)
+                                            >{format!("Принять все ({})", ack_ids.len())}</thaw::Button>
+                                            {move || ack_error.get().map(|e| view! { <span style="color: var(--color-error); font-size: 0.82rem;">{e}</span> })}
+                                        </div>
+                                    }
+                                })}
                                 <table class="table__data" style="font-size: 0.82rem;">
                                     <thead class="table__head">
                                         <tr>
@@ -366,6 +427,7 @@ pub fn QualityCheckDetails(check_id: String) -> impl IntoView {
                                             <th class="table__header-cell">"GL"</th>
                                             <th class="table__header-cell">"Таблица"</th>
                                             <th class="table__header-cell">"Детали"</th>
+                                            <th class="table__header-cell"></th>
                                         </tr>
                                     </thead>
                                     <tbody>
@@ -375,8 +437,16 @@ pub fn QualityCheckDetails(check_id: String) -> impl IntoView {
                                             "orphan_projection" => "Строка без GL",
                                             "amount_mismatch" => "Расхождение суммы",
                                             "missing_marketplace_product_ref" => "Нет товара МП",
+                                            "unknown_field" => "Новое поле",
+                                            "missing_field" => "Пропало поле",
+                                            "row_rejected" => "Строка не разобрана",
                                             _ => "Нарушение",
                                         };
+                                        let ack_id = v
+                                            .projection_id
+                                            .clone()
+                                            .filter(|_| is_acknowledgeable(&v.violation_type));
+                                        let cid_row = cid_ack.clone();
                                         let proj_table = v.projection_table.clone().unwrap_or_default();
                                         let detail = v.detail.clone().unwrap_or_default();
                                         let gl_opt = v.gl_id.clone();
@@ -405,6 +475,15 @@ pub fn QualityCheckDetails(check_id: String) -> impl IntoView {
                                                 </td>
                                                 <td class="table__cell" style="color: var(--color-text-secondary); font-size: 0.78rem;">{proj_table}</td>
                                                 <td class="table__cell" style="color: var(--color-text-secondary); font-size: 0.78rem; font-family: monospace;">{detail}</td>
+                                                <td class="table__cell">
+                                                    {ack_id.map(|id| view! {
+                                                        <thaw::Button
+                                                            appearance=thaw::ButtonAppearance::Subtle
+                                                            size=thaw::ButtonSize::Small
+                                                            on_click=move |_| acknowledge_violations(cid_row.clone(), vec![id.clone()], set_reload, set_ack_error)
+                                                        >"Принять"</thaw::Button>
+                                                    })}
+                                                </td>
                                             </tr>
                                         }
                                     }).collect_view()}
@@ -6,9 +6,11 @@
 
 use crate::shared::api_utils::api_base;
 use crate::shared::icons::icon;
+use crate::system::environment::confirm::confirm_danger;
 use contracts::quality::{
     NipCleanupResult, NipGroupsResponse, NipProjectionRow, NipRegistratorGroup, NipRepostResult,
 };
+use contracts::system::environment::CONFIRM_TOKEN_HEADER;
 use gloo_net::http::Request;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
@@ -29,6 +31,16 @@ pub fn is_cleanup_check(check_id: &str) -> bool {
     check_id == "projection_orphan_registrators"
 }
 
+/// Токен подтверждения очистки на staging/prod; `Err` — пользователь отказался.
+async fn cleanup_confirm_token(check_id: &str) -> Result<Option<String>, String> {
+    confirm_danger(
+        "POST",
+        &format!("/api/quality/checks/{}/cleanup", check_id),
+        "удаление строк проекций без регистратора",
+    )
+    .await
+}
+
 // ---------------------------------------------------------------------------
 // RegistratorGroupsPanel — список регистраторов с пагинацией и сортировкой
 // ---------------------------------------------------------------------------
@@ -203,6 +215,18 @@ pub fn RegistratorGroupsPanel(
                     cid,
                     if cleanup_mode { "cleanup" } else { "repost" }
                 );
+                let confirm_token = if cleanup_mode {
+                    match cleanup_confirm_token(&cid).await {
+                        Ok(token) => token,
+                        Err(err) => {
+                            set_repost_msg.set(Some(err));
+                            set_repost_loading.set(false);
+                            return;
+                        }
+                    }
+                } else {
+                    None
+                };
                 let mut completed = 0usize;
                 let mut affected = 0usize;
                 let mut errors: Vec<String> = Vec::new();
@@ -221,13 +245,12 @@ pub fn RegistratorGroupsPanel(
                         })
                     };
 
-                    match Request::post(&url)
-                        .header("Content-Type", "application/json")
-                        .body(body.to_string())
-                        .unwrap()
-                        .send()
-                        .await
-                    {
+                    let mut request =
+                        Request::post(&url).header("Content-Type", "application/json");
+                    if let Some(token) = &confirm_token {
+                        request = request.header(CONFIRM_TOKEN_HEADER, token);
+                    }
+                    match request.body(body.to_string()).unwrap().send().await {
                         Ok(resp) if resp.status() == 200 => {
                             if cleanup_mode {
                                 if let Ok(result) = resp.json::<NipCleanupResult>().await {
@@ -547,8 +570,22 @@ pub fn RegistratorGroupsPanel(
                                                                                         cid_a,
                                                                                         if cleanup_mode { "cleanup" } else { "repost" }
                                                                                     );
-                                                                                    match Request::post(&url)
-                                                                                        .header("Content-Type", "application/json")
+                                                                                    let mut request = Request::post(&url)
+                                                                                        .header("Content-Type", "application/json");
+                                                                                    if cleanup_mode {
+                                                                                        match cleanup_confirm_token(&cid_a).await {
+                                                                                            Ok(Some(token)) => {
+                                                                                                request = request.header(CONFIRM_TOKEN_HEADER, &token);
+                                                                                            }
+                                                                                            Ok(None) => {}
+                                                                                            Err(err) => {
+                                                                                                set_repost_msg.set(Some(err));
+                                                                                                set_repost_loading.set(false);
+                                                                                                return;
+                                                                                            }
+                                                                                        }
+                                                                                    }
+                                                                                    match request
                                                                                         .body(body.to_string())
                                                                                         .unwrap()
                                                                                         .send()
@@ -0,0 +1,300 @@
+//! Единый клиент backend API: базовый URL, заголовок авторизации, разбор JSON
+//! и типизированные ошибки.
+//!
+//! Базовый URL по порядку: заданный через [`set_base_url`], затем
+//! `<meta name="api-base-url" content="...">` из `index.html`, иначе origin
+//! текущей страницы — так фронтенд работает за reverse proxy и на любом хосте.
+//!
+//! ```rust
+//! let dto: WbOrderDetailDto = api_client::get_json(&format!("/api/a015/wb-orders/{}", id)).await?;
+//! ```
+
+use std::cell::RefCell;
+use std::fmt;
+
+use contracts::domain::common::VersionConflict;
+use contracts::shared::api_error::ApiErrorBody;
+use gloo_net::http::{Request, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::shared::error_toasts;
+use crate::system::auth::storage;
+
+/// Заголовок с кодом запроса, который backend проставляет в каждый ответ.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+thread_local! {
+    static BASE_URL_OVERRIDE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Задаёт базовый URL явно; `None` — снова определять по странице.
+pub fn set_base_url(url: Option<String>) {
+    let url = url
+        .map(|u| u.trim().trim_end_matches('/').to_string())
+        .filter(|u| !u.is_empty());
+    BASE_URL_OVERRIDE.with(|o| *o.borrow_mut() = url);
+}
+
+fn meta_base_url(window: &web_sys::Window) -> Option<String> {
+    let content = window
+        .document()?
+        .query_selector("meta[name='api-base-url']")
+        .ok()??
+        .get_attribute("content")?;
+    let content = content.trim().trim_end_matches('/');
+    (!content.is_empty()).then(|| content.to_string())
+}
+
+/// Базовый URL API без завершающего `/`; пустая строка вне браузера.
+pub fn base_url() -> String {
+    if let Some(url) = BASE_URL_OVERRIDE.with(|o| o.borrow().clone()) {
+        return url;
+    }
+    let Some(window) = web_sys::window() else {
+        return String::new();
+    };
+    meta_base_url(&window).unwrap_or_else(|| window.location().origin().unwrap_or_default())
+}
+
+/// Полный URL по пути API (`/api/...`).
+pub fn url(path: &str) -> String {
+    format!("{}{}", base_url(), path)
+}
+
+/// Путь команды над документом с версией для optimistic locking (`?version=N`).
+pub fn with_version(path: &str, version: Option<i32>) -> String {
+    match version {
+        Some(version) => format!("{}?version={}", path, version),
+        None => path.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    /// Запрос не дошёл до сервера.
+    Network(String),
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    /// Ответ не 2xx с JSON-конвертом ошибки backend.
+    Server {
+        status: u16,
+        error: ApiErrorBody,
+    },
+    /// Прочие ответы не 2xx; `message` — тело ответа, если есть.
+    Status {
+        status: u16,
+        message: String,
+        request_id: Option<String>,
+    },
+    /// Ответ пришёл, но не разобран.
+    Parse(String),
+}
+
+impl ApiError {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Unauthorized => Some(401),
+            Self::Forbidden => Some(403),
+            Self::NotFound => Some(404),
+            Self::Server { status, .. } | Self::Status { status, .. } => Some(*status),
+            Self::Network(_) | Self::Parse(_) => None,
+        }
+    }
+
+    /// Код запроса из ответа сервера — его пользователь сообщает в поддержку.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Self::Server { error, .. } => error.correlation_id.as_deref(),
+            Self::Status { request_id, .. } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Конфликт версий документа (409 от команд с `?version=N`).
+    pub fn version_conflict(&self) -> Option<VersionConflict> {
+        match self {
+            Self::Server { status: 409, error } => error
+                .details
+                .clone()
+                .and_then(|details| serde_json::from_value(details).ok()),
+            Self::Status {
+                status: 409,
+                message,
+                ..
+            } => serde_json::from_str(message).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Network(e) => write!(f, "Network error: {}", e),
+            Self::Unauthorized => write!(f, "Not authenticated (HTTP 401)"),
+            Self::Forbidden => write!(f, "Access denied (HTTP 403)"),
+            Self::NotFound => write!(f, "Not found (HTTP 404)"),
+            Self::Server { error, .. } => match &error.correlation_id {
+                Some(id) => write!(f, "{} (запрос {})", error.message, id),
+                None => write!(f, "{}", error.message),
+            },
+            Self::Status {
+                status,
+                message,
+                request_id,
+            } => {
+                write!(f, "Server error: {}", status)?;
+                if !message.is_empty() {
+                    write!(f, ": {}", message)?;
+                }
+                match request_id {
+                    Some(id) => write!(f, " (запрос {})", id),
+                    None => Ok(()),
+                }
+            }
+            Self::Parse(e) => write!(f, "Failed to parse response: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Модули со строковыми ошибками используют клиент через `?`.
+impl From<ApiError> for String {
+    fn from(err: ApiError) -> Self {
+        err.to_string()
+    }
+}
+
+fn with_auth(builder: RequestBuilder) -> RequestBuilder {
+    match storage::get_access_token() {
+        Some(token) => builder.header("Authorization", &format!("Bearer {}", token)),
+        None => builder,
+    }
+}
+
+async fn check(response: Response) -> Result<Response, ApiError> {
+    match response.status() {
+        200..=299 => Ok(response),
+        401 => Err(ApiError::Unauthorized),
+        403 => Err(ApiError::Forbidden),
+        404 => Err(ApiError::NotFound),
+        status => {
+            let request_id = response.headers().get(REQUEST_ID_HEADER);
+            let message = response.text().await.unwrap_or_default();
+            let err = match serde_json::from_str::<ApiErrorBody>(&message) {
+                Ok(mut error) => {
+                    if error.correlation_id.is_none() {
+                        error.correlation_id = request_id;
+                    }
+                    ApiError::Server { status, error }
+                }
+                Err(_) => ApiError::Status {
+                    status,
+                    message: message.trim().chars().take(300).collect(),
+                    request_id,
+                },
+            };
+            if status >= 500 {
+                report(&err);
+            }
+            Err(err)
+        }
+    }
+}
+
+/// Сбой на стороне сервера показываем уведомлением с кодом запроса,
+/// даже если страница выводит ошибку по-своему.
+fn report(err: &ApiError) {
+    let message = match err {
+        ApiError::Server { error, .. } => error.message.clone(),
+        ApiError::Status { status, .. } => format!("Ошибка сервера (HTTP {})", status),
+        other => other.to_string(),
+    };
+    error_toasts::push_error(message, err.request_id().map(str::to_string));
+}
+
+async fn send(builder: RequestBuilder) -> Result<Response, ApiError> {
+    let response = with_auth(builder)
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+    check(response).await
+}
+
+async fn send_json<B: Serialize + ?Sized>(
+    builder: RequestBuilder,
+    body: &B,
+) -> Result<Response, ApiError> {
+    let request: Request = with_auth(builder)
+        .json(body)
+        .map_err(|e| ApiError::Parse(e.to_string()))?;
+    let response = request
+        .send()
+        .await
+        .map_err(|e| ApiError::Network(e.to_string()))?;
+    check(response).await
+}
+
+async fn parse<T: DeserializeOwned>(response: Response) -> Result<T, ApiError> {
+    response
+        .json()
+        .await
+        .map_err(|e| ApiError::Parse(e.to_string()))
+}
+
+/// GET с JSON-ответом.
+pub async fn get_json<T: DeserializeOwned>(path: &str) -> Result<T, ApiError> {
+    parse(send(Request::get(&url(path))).await?).await
+}
+
+/// GET с текстовым ответом.
+pub async fn get_text(path: &str) -> Result<String, ApiError> {
+    send(Request::get(&url(path)))
+        .await?
+        .text()
+        .await
+        .map_err(|e| ApiError::Parse(e.to_string()))
+}
+
+/// GET, где 404 — штатное «нет данных».
+pub async fn get_json_opt<T: DeserializeOwned>(path: &str) -> Result<Option<T>, ApiError> {
+    match get_json(path).await {
+        Ok(value) => Ok(Some(value)),
+        Err(ApiError::NotFound) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// POST с JSON-телом и JSON-ответом.
+pub async fn post_json<B: Serialize + ?Sized, T: DeserializeOwned>(
+    path: &str,
+    body: &B,
+) -> Result<T, ApiError> {
+    parse(send_json(Request::post(&url(path)), body).await?).await
+}
+
+/// POST без тела; ответ не разбирается (команды вроде проведения документа).
+pub async fn post_empty(path: &str) -> Result<(), ApiError> {
+    send(Request::post(&url(path))).await.map(|_| ())
+}
+
+/// PUT с JSON-телом; ответ не разбирается.
+pub async fn put_json<B: Serialize + ?Sized>(path: &str, body: &B) -> Result<(), ApiError> {
+    send_json(Request::put(&url(path)), body).await.map(|_| ())
+}
+
+/// DELETE; ответ не разбирается.
+pub async fn delete(path: &str) -> Result<(), ApiError> {
+    send(Request::delete(&url(path))).await.map(|_| ())
+}
+
+/// DELETE с JSON-телом и JSON-ответом (отвязка нескольких элементов за раз).
+pub async fn delete_json<B: Serialize + ?Sized, T: DeserializeOwned>(
+    path: &str,
+    body: &B,
+) -> Result<T, ApiError> {
+    parse(send_json(Request::delete(&url(path)), body).await?).await
+}
@@ -4,11 +4,11 @@
 
 /// Get the base URL for API requests
 ///
-/// Constructs the API base URL from the current window location,
-/// using port 3000 for the backend server.
+/// Delegates to [`crate::shared::api_client::base_url`]: explicit override,
+/// `<meta name="api-base-url">`, or the origin of the current page.
 ///
 /// # Returns
-/// - API base URL like "http://localhost:3000" or "https://example.com:3000"
+/// - API base URL like "http://localhost:3000" or "https://example.com"
 /// - Empty string if window is not available
 ///
 /// # Example
@@ -16,28 +16,7 @@
 /// let url = format!("{}/api/nomenclature/{}", api_base(), id);
 /// ```
 pub fn api_base() -> String {
-    let window = match web_sys::window() {
-        Some(w) => w,
-        None => return String::new(),
-    };
-    let location = window.location();
-    let protocol = location.protocol().unwrap_or_else(|_| "http:".to_string());
-
-    // Используем host (включает порт), а не hostname + :3000
-    // Это работает когда backend и frontend на одном порту
-    let host = location
-        .host()
-        .unwrap_or_else(|_| "127.0.0.1:3000".to_string());
-
-    // Если host уже содержит порт, используем как есть
-    // Иначе добавляем :3000
-    let full_host = if host.contains(':') {
-        host
-    } else {
-        format!("{}:3000", host)
-    };
-
-    format!("{}//{}", protocol, full_host)
+    crate::shared::api_client::base_url()
 }
 
 /// Build a full API URL from a path
@@ -0,0 +1,70 @@
+//! Метка ABC/XYZ-класса товара (task027) для списков и дашбордов.
+//!
+//! Цвет — по ABC (A — основная выручка, C — хвост), а «CZ» выделен как
+//! кандидат в неликвид. В тултипе — квартал, доля выручки и вариация спроса.
+
+use crate::shared::components::ui::badge::Badge;
+use crate::shared::money_format::format_percent;
+use contracts::domain::a007_marketplace_product::aggregate::{AbcClass, AbcXyzLabelDto, XyzClass};
+use leptos::prelude::*;
+
+fn variant(label: &AbcXyzLabelDto) -> &'static str {
+    match (label.abc, label.xyz) {
+        (AbcClass::C, XyzClass::Z) => "error",
+        (AbcClass::A, _) => "success",
+        (AbcClass::B, _) => "primary",
+        (AbcClass::C, _) => "warning",
+    }
+}
+
+fn title(label: &AbcXyzLabelDto) -> String {
+    let cv = label
+        .demand_cv
+        .map(|cv| format_percent(cv, 0))
+        .unwrap_or_else(|| "нет продаж".to_string());
+    format!(
+        "{}: доля выручки {}, вариация спроса {}",
+        label.quarter,
+        format_percent(label.revenue_share, 2),
+        cv
+    )
+}
+
+/// Бейдж класса; без метки (товар не классифицирован) — пусто.
+pub fn abc_xyz_badge(label: Option<AbcXyzLabelDto>) -> AnyView {
+    match label {
+        Some(label) => {
+            let title = title(&label);
+            let variant = variant(&label).to_string();
+            view! {
+                <span title=title>
+                    <Badge variant=variant>{label.code()}</Badge>
+                </span>
+            }
+            .into_any()
+        }
+        None => view! { <></> }.into_any(),
+    }
+}
+
+/// Опции фильтра по ABC-классу: `("", "Все")`, `("A", "A")`, …
+pub fn abc_filter_options() -> Vec<(&'static str, &'static str)> {
+    std::iter::once(("", "Все"))
+        .chain(
+            AbcClass::ALL
+                .iter()
+                .map(|class| (class.code(), class.code())),
+        )
+        .collect()
+}
+
+/// Опции фильтра по XYZ-классу.
+pub fn xyz_filter_options() -> Vec<(&'static str, &'static str)> {
+    std::iter::once(("", "Все"))
+        .chain(
+            XyzClass::ALL
+                .iter()
+                .map(|class| (class.code(), class.code())),
+        )
+        .collect()
+}
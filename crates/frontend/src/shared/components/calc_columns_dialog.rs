@@ -0,0 +1,131 @@
+//! `CalcColumnsDialog` — редактор вычисляемых колонок списка
+//! (`Маржа = amount_line - finished_price`).
+//!
+//! Проверка выражений общая с сервером (`contracts::shared::calc_column`), поэтому
+//! ошибка видна в диалоге до применения. Сохраняются колонки вместе с остальными
+//! настройками формы (кнопка «Сохранить настройки» списка).
+
+use contracts::shared::calc_column::{
+    validate_columns, CalcColumnDef, CalcField, MAX_CALC_COLUMNS,
+};
+use leptos::prelude::*;
+use thaw::*;
+
+#[component]
+pub fn CalcColumnsDialog(
+    open: RwSignal<bool>,
+    /// Текущие колонки списка (черновик диалога заполняется при открытии)
+    #[prop(into)]
+    columns: Signal<Vec<CalcColumnDef>>,
+    /// Поля, доступные в выражениях для этого списка
+    fields: &'static [CalcField],
+    on_apply: Callback<Vec<CalcColumnDef>>,
+) -> impl IntoView {
+    let draft = RwSignal::new(Vec::<CalcColumnDef>::new());
+
+    Effect::new(move |_| {
+        if open.get() {
+            draft.set(columns.get_untracked());
+        }
+    });
+
+    let error = Memo::new(move |_| draft.with(|d| validate_columns(d, fields).err()));
+    let help = fields
+        .iter()
+        .map(|f| format!("{} — {}", f.key, f.label))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let update = move |index: usize, apply: fn(&mut CalcColumnDef, String), value: String| {
+        draft.update(|d| {
+            if let Some(def) = d.get_mut(index) {
+                apply(def, value);
+            }
+        });
+    };
+
+    view! {
+        <Dialog open=open>
+            <DialogSurface>
+                <DialogBody>
+                    <DialogTitle>"Вычисляемые колонки"</DialogTitle>
+                    <DialogContent>
+                        <div style="font-size: var(--font-size-sm); color: var(--color-text-secondary); margin-bottom: var(--spacing-sm);">
+                            "Арифметика + − × / и скобки над числовыми полями. Наведите на поле выражения, чтобы увидеть список полей."
+                        </div>
+                        {move || {
+                            draft
+                                .get()
+                                .into_iter()
+                                .enumerate()
+                                .map(|(index, def)| {
+                                    view! {
+                                        <div style="display: flex; gap: var(--spacing-xs); margin-bottom: var(--spacing-xs);">
+                                            <input
+                                                class="form__input"
+                                                type="text"
+                                                style="width: 160px;"
+                                                placeholder="Название"
+                                                prop:value=def.name
+                                                on:change=move |ev| {
+                                                    update(index, |d, v| d.name = v, event_target_value(&ev))
+                                                }
+                                            />
+                                            <input
+                                                class="form__input"
+                                                type="text"
+                                                style="flex: 1; font-family: var(--font-mono, monospace);"
+                                                spellcheck="false"
+                                                title=help.clone()
+                                                placeholder="amount_line - finished_price"
+                                                prop:value=def.expr
+                                                on:change=move |ev| {
+                                                    update(index, |d, v| d.expr = v, event_target_value(&ev))
+                                                }
+                                            />
+                                            <Button
+                                                appearance=ButtonAppearance::Subtle
+                                                on_click=move |_| draft.update(|d| {
+                                                    d.remove(index);
+                                                })
+                                            >
+                                                "✕"
+                                            </Button>
+                                        </div>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                        <Button
+                            appearance=ButtonAppearance::Subtle
+                            disabled=Signal::derive(move || draft.with(|d| d.len() >= MAX_CALC_COLUMNS))
+                            on_click=move |_| draft.update(|d| d.push(CalcColumnDef::default()))
+                        >
+                            "+ Добавить колонку"
+                        </Button>
+                        {move || error.get().map(|e| view! {
+                            <div style="margin-top: var(--spacing-sm); color: var(--color-error); font-size: var(--font-size-sm);">
+                                {e}
+                            </div>
+                        })}
+                    </DialogContent>
+                    <DialogActions>
+                        <Button appearance=ButtonAppearance::Secondary on_click=move |_| open.set(false)>
+                            "Отмена"
+                        </Button>
+                        <Button
+                            appearance=ButtonAppearance::Primary
+                            disabled=Signal::derive(move || error.get().is_some())
+                            on_click=move |_| {
+                                open.set(false);
+                                on_apply.run(draft.get_untracked());
+                            }
+                        >
+                            "Применить"
+                        </Button>
+                    </DialogActions>
+                </DialogBody>
+            </DialogSurface>
+        </Dialog>
+    }
+}
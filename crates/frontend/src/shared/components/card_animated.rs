@@ -58,7 +58,7 @@ pub fn CardAnimated(
         if let Some(auth_state) =
             use_context::<ReadSignal<crate::system::auth::context::AuthState>>()
         {
-            let user_info = auth_state.with_untracked(|s| s.user_info.clone());
+            let user_info = auth_state.with_untracked(|s| s.effective_user());
             if let Some(user) = user_info {
                 if !ui_access_allowed(&nav_id, &user.primary_role, user.is_admin) {
                     return ().into_any();
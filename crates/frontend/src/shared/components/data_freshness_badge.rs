@@ -0,0 +1,79 @@
+//! Бейдж свежести данных «данные на 06:32» для списков и дашбордов.
+//!
+//! Источник старше SLA подсвечивается предупреждением; в тултипе — полное время
+//! последнего импорта и SLA. Если источников несколько, к каждому добавляется
+//! его название.
+
+use crate::shared::components::ui::badge::Badge;
+use crate::shared::date_utils::{format_utc_local, TZ_OFFSET_HOURS};
+use chrono::{DateTime, Duration, Utc};
+use contracts::system::data_freshness::DataFreshnessDto;
+use leptos::prelude::*;
+
+fn local_day(dt: &DateTime<Utc>) -> chrono::NaiveDate {
+    (*dt + Duration::hours(TZ_OFFSET_HOURS as i64)).date_naive()
+}
+
+fn badge_text(item: &DataFreshnessDto, with_label: bool) -> String {
+    let at = item
+        .last_import_at
+        .as_deref()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+    let when = match at {
+        Some(at) if local_day(&at) == local_day(&Utc::now()) => {
+            format!("данные на {}", format_utc_local(&at, "%H:%M"))
+        }
+        Some(at) => format!("данные на {}", format_utc_local(&at, "%d.%m %H:%M")),
+        None => "импорта не было".to_string(),
+    };
+    if with_label {
+        format!("{}: {}", item.label, when)
+    } else {
+        when
+    }
+}
+
+fn badge_title(item: &DataFreshnessDto) -> String {
+    let last = item
+        .last_import_at
+        .as_deref()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| format_utc_local(&dt.with_timezone(&Utc), "%d.%m.%Y %H:%M"))
+        .unwrap_or_else(|| "—".to_string());
+    format!(
+        "{}: последний импорт {}, SLA {} ч{}",
+        item.label,
+        last,
+        item.sla_hours,
+        if item.stale {
+            " — данные устарели"
+        } else {
+            ""
+        }
+    )
+}
+
+/// Бейджи свежести; без данных (не загружено) — пусто.
+#[component]
+pub fn DataFreshnessBadges(#[prop(into)] items: Signal<Vec<DataFreshnessDto>>) -> impl IntoView {
+    view! {
+        {move || {
+            let items = items.get();
+            let with_label = items.len() > 1;
+            items
+                .into_iter()
+                .map(|item| {
+                    let title = badge_title(&item);
+                    let text = badge_text(&item, with_label);
+                    let variant = if item.stale { "warning" } else { "neutral" }.to_string();
+                    view! {
+                        <span class="data-freshness" title=title>
+                            <Badge variant=variant>{text}</Badge>
+                        </span>
+                    }
+                })
+                .collect_view()
+        }}
+    }
+}
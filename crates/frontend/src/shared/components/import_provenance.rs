@@ -0,0 +1,121 @@
+//! ImportProvenanceSection — раздел «Происхождение импорта» для карточек документов
+//! и запусков задач.
+//!
+//! Данные — `GET /api/sys/import-provenance` (`sys_import_batches`): по каждому пакету,
+//! полученному от API, параметры запроса, sha256 тела ответа и число строк. Аудитор
+//! может сверить контрольную сумму с повторной выгрузкой за те же параметры.
+
+use crate::shared::api_client;
+use crate::shared::date_utils::{format_bytes_compact, format_datetime_utc_local};
+use contracts::system::import_provenance::ImportProvenanceResponse;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::*;
+
+async fn fetch_provenance(
+    session_id: &str,
+    aggregate_index: Option<&str>,
+) -> Result<ImportProvenanceResponse, String> {
+    let mut path = format!(
+        "/api/sys/import-provenance?session_id={}",
+        urlencoding::encode(session_id)
+    );
+    if let Some(aggregate_index) = aggregate_index {
+        path.push_str(&format!("&aggregate_index={}", aggregate_index));
+    }
+    api_client::get_json(&path).await.map_err(|e| e.to_string())
+}
+
+#[component]
+pub fn ImportProvenanceSection(
+    /// Сессия импорта (`source_meta.import_session_id` или `sys_task_runs.session_id`)
+    session_id: String,
+    /// Только пакеты одного агрегата; `None` — все пакеты сессии
+    #[prop(optional)]
+    aggregate_index: Option<&'static str>,
+) -> impl IntoView {
+    let data = RwSignal::new(None::<ImportProvenanceResponse>);
+    let error = RwSignal::new(None::<String>);
+
+    spawn_local(async move {
+        match fetch_provenance(&session_id, aggregate_index).await {
+            Ok(response) => data.set(Some(response)),
+            Err(e) => error.set(Some(e)),
+        }
+    });
+
+    view! {
+        <div class="details-section">
+            <h4 class="details-section__title">"Происхождение импорта"</h4>
+            {move || error.get().map(|e| view! {
+                <div class="alert alert--error">{format!("Не удалось загрузить: {}", e)}</div>
+            })}
+            {move || match data.get() {
+                None if error.get().is_none() => view! { <Spinner size=SpinnerSize::Tiny /> }.into_any(),
+                None => view! { <></> }.into_any(),
+                Some(response) if response.batches.is_empty() => view! {
+                    <div style="color: var(--color-text-secondary);">
+                        "Пакеты импорта для этой сессии не записаны"
+                    </div>
+                }.into_any(),
+                Some(response) => view! {
+                    <div style="margin-bottom: var(--spacing-sm); color: var(--color-text-secondary); font-size: 13px;">
+                        {format!(
+                            "Сессия {} · пакетов {} · строк {} · {}",
+                            response.session_id,
+                            response.batches.len(),
+                            response.total_rows,
+                            format_bytes_compact(response.total_bytes.max(0) as u64)
+                        )}
+                    </div>
+                    <Table attr:style="width: 100%;">
+                        <TableHeader>
+                            <TableRow>
+                                <TableHeaderCell>"Получено"</TableHeaderCell>
+                                <TableHeaderCell>"Агрегат"</TableHeaderCell>
+                                <TableHeaderCell>"Запрос"</TableHeaderCell>
+                                <TableHeaderCell>"Строк"</TableHeaderCell>
+                                <TableHeaderCell>"Размер"</TableHeaderCell>
+                                <TableHeaderCell>"SHA-256 ответа"</TableHeaderCell>
+                            </TableRow>
+                        </TableHeader>
+                        <TableBody>
+                            {response.batches.into_iter().map(|batch| view! {
+                                <TableRow>
+                                    <TableCell>
+                                        <TableCellLayout>
+                                            {format_datetime_utc_local(&batch.fetched_at, "%d.%m.%Y %H:%M:%S")}
+                                        </TableCellLayout>
+                                    </TableCell>
+                                    <TableCell>
+                                        <TableCellLayout>{batch.aggregate_index}</TableCellLayout>
+                                    </TableCell>
+                                    <TableCell>
+                                        <TableCellLayout truncate=true>
+                                            <span title=batch.endpoint.clone()>{batch.request_params}</span>
+                                        </TableCellLayout>
+                                    </TableCell>
+                                    <TableCell>
+                                        <TableCellLayout>{batch.row_count}</TableCellLayout>
+                                    </TableCell>
+                                    <TableCell>
+                                        <TableCellLayout>
+                                            {format_bytes_compact(batch.response_bytes.max(0) as u64)}
+                                        </TableCellLayout>
+                                    </TableCell>
+                                    <TableCell>
+                                        <TableCellLayout>
+                                            <code style="font-size: 12px; word-break: break-all;">
+                                                {batch.response_sha256}
+                                            </code>
+                                        </TableCellLayout>
+                                    </TableCell>
+                                </TableRow>
+                            }).collect_view()}
+                        </TableBody>
+                    </Table>
+                }.into_any(),
+            }}
+        </div>
+    }
+}
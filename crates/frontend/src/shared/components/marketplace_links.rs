@@ -0,0 +1,40 @@
+//! MarketplaceLinkButtons — кнопки «открыть на маркетплейсе» для `page__header-right`.
+//!
+//! Ссылки приходят готовыми в детальном DTO (`marketplace_links`), форматы URL
+//! живут на backend. Пока документ не загружен или ссылок нет, ничего не выводится.
+
+use crate::shared::icons::icon;
+use contracts::shared::marketplace_links::MarketplaceLinkDto;
+use leptos::prelude::*;
+use thaw::{Button, ButtonAppearance, ButtonSize};
+
+#[component]
+pub fn MarketplaceLinkButtons(
+    #[prop(into)] links: Signal<Vec<MarketplaceLinkDto>>,
+) -> impl IntoView {
+    view! {
+        <For
+            each=move || links.get()
+            key=|link| link.url.clone()
+            children=move |link| {
+                let url = link.url.clone();
+                view! {
+                    <Button
+                        appearance=ButtonAppearance::Subtle
+                        size=ButtonSize::Medium
+                        on_click=move |_| {
+                            if let Some(win) = web_sys::window() {
+                                let _ = win.open_with_url_and_target(&url, "_blank");
+                            }
+                        }
+                    >
+                        <span class="page-action-button__content">
+                            <span class="page-action-button__icon">{icon("link")}</span>
+                            <span class="page-action-button__text">{link.title}</span>
+                        </span>
+                    </Button>
+                }
+            }
+        />
+    }
+}
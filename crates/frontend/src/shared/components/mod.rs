@@ -1,16 +1,27 @@
+pub mod abc_xyz_badge;
+pub mod calc_columns_dialog;
 pub mod card_animated;
 pub mod close_page_button;
+pub mod data_freshness_badge;
 pub mod date_input;
 pub mod date_range_picker;
 pub mod date_range_picker_smart;
 pub mod filter_panel;
+pub mod import_provenance;
+pub mod marketplace_links;
 pub mod month_selector;
 pub mod more_actions_menu;
 pub mod page_header;
 pub mod pagination_controls;
 pub mod popover;
+pub mod quick_filter_input;
+pub mod raw_payload_versions;
+pub mod row_context_menu;
+pub mod sandbox_badge;
+pub mod sku_sparkline;
 pub mod sql_viewer;
 pub mod table;
 pub mod table_checkbox;
 pub mod table_totals_row;
 pub mod ui;
+pub mod version_conflict_dialog;
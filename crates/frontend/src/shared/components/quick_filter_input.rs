@@ -0,0 +1,126 @@
+//! `QuickFilterInput` — строка поиска списка с языком быстрых фильтров
+//! (`status:unposted warehouse:"Коледино" amount>1000`) и автодополнением полей.
+//!
+//! Разбор и подсказки — общие с сервером (`contracts::shared::quick_filter`), поэтому
+//! ошибка в строке показывается сразу под полем, до запроса к API.
+
+use contracts::shared::quick_filter::{parse_for, suggest, QuickFilterField, QuickFilterKind};
+use leptos::prelude::*;
+
+fn field_hint(field: &QuickFilterField) -> String {
+    let syntax = match field.kind {
+        QuickFilterKind::Text => format!("{}:текст", field.key),
+        QuickFilterKind::Number => format!("{}>число", field.key),
+        QuickFilterKind::Date => format!("{}:ГГГГ-ММ-ДД", field.key),
+        QuickFilterKind::Enum(values) => format!("{}:{}", field.key, values.join("|")),
+    };
+    format!("{} — {}", syntax, field.label)
+}
+
+#[component]
+pub fn QuickFilterInput(
+    /// Строка фильтра (уходит в API как есть)
+    value: RwSignal<String>,
+    /// Поля, которые понимает сервер для этого списка
+    fields: &'static [QuickFilterField],
+    #[prop(optional, into)] placeholder: MaybeProp<String>,
+) -> impl IntoView {
+    let focused = RwSignal::new(false);
+    let highlighted = RwSignal::new(0usize);
+
+    let suggestions = Memo::new(move |_| value.with(|v| suggest(fields, v)));
+    let error = Memo::new(move |_| value.with(|v| parse_for(fields, v).err()));
+    let open = move || focused.get() && !suggestions.with(|s| s.is_empty());
+
+    let apply = move |index: usize| {
+        if let Some(s) = suggestions.with_untracked(|s| s.get(index).cloned()) {
+            value.set(s.replacement);
+            highlighted.set(0);
+        }
+    };
+
+    let help = fields.iter().map(field_hint).collect::<Vec<_>>().join("\n");
+
+    view! {
+        <div style="position: relative;">
+            <input
+                class="form__input"
+                type="text"
+                autocomplete="off"
+                spellcheck="false"
+                title=help
+                placeholder=move || placeholder.get().unwrap_or_default()
+                prop:value=move || value.get()
+                on:input=move |ev| {
+                    value.set(event_target_value(&ev));
+                    highlighted.set(0);
+                }
+                on:focus=move |_| focused.set(true)
+                on:blur=move |_| focused.set(false)
+                on:keydown=move |ev| {
+                    if !open() {
+                        return;
+                    }
+                    let count = suggestions.with_untracked(|s| s.len());
+                    match ev.key().as_str() {
+                        "ArrowDown" => {
+                            ev.prevent_default();
+                            highlighted.update(|i| *i = (*i + 1) % count);
+                        }
+                        "ArrowUp" => {
+                            ev.prevent_default();
+                            highlighted.update(|i| *i = (*i + count - 1) % count);
+                        }
+                        "Tab" | "Enter" => {
+                            ev.prevent_default();
+                            apply(highlighted.get_untracked());
+                        }
+                        "Escape" => focused.set(false),
+                        _ => {}
+                    }
+                }
+            />
+            <Show when=open>
+                <div style="position: absolute; left: 0; right: 0; top: calc(100% + 2px); z-index: 1000; \
+                            background: var(--color-menu-surface, var(--color-surface)); \
+                            border: 1px solid var(--color-border); border-radius: var(--radius-md); \
+                            box-shadow: 0 8px 28px rgba(0,0,0,.32); padding: var(--spacing-xs) 0; \
+                            max-height: 260px; overflow-y: auto;">
+                    {move || {
+                        suggestions
+                            .get()
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, s)| {
+                                // mousedown, а не click: иначе blur поля закроет список раньше
+                                view! {
+                                    <button
+                                        type="button"
+                                        class=move || {
+                                            if highlighted.get() == index {
+                                                "theme-dropdown__item theme-dropdown__item--active"
+                                            } else {
+                                                "theme-dropdown__item"
+                                            }
+                                        }
+                                        on:mousedown=move |ev| {
+                                            ev.prevent_default();
+                                            apply(index);
+                                        }
+                                    >
+                                        {s.label}
+                                    </button>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </div>
+            </Show>
+            {move || error.get().map(|err| view! {
+                <div style="color: var(--color-error); font-size: var(--font-size-xs); margin-top: 2px;">
+                    {err}
+                </div>
+            })}
+        </div>
+    }
+}
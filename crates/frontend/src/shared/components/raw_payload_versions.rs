@@ -0,0 +1,273 @@
+//! RawPayloadVersions — история raw JSON документа во вкладке «Raw JSON».
+//!
+//! При повторной загрузке документа с изменившимся телом ответа backend пишет
+//! новую строку `document_raw_storage`, прежние остаются (очистка хранит последние
+//! `RAW_VERSIONS_KEPT`). Компонент получает список версий через
+//! `GET {raw_base}/{ref}/versions`, позволяет выбрать любую и показывает построчный
+//! diff отформатированного JSON относительно текущей версии.
+//!
+//! ```rust
+//! view! { <RawPayloadVersions raw_base="/api/a012/raw" current_ref=current_ref current_json=json /> }
+//! ```
+
+use crate::shared::api_client;
+use crate::shared::date_utils::{format_bytes_compact, format_datetime_utc_local};
+use crate::shared::json_viewer::widget::JsonViewer;
+use contracts::system::raw_storage::RawPayloadVersionDto;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::*;
+
+/// Сколько неизменённых строк показываем вокруг каждого изменения.
+const DIFF_CONTEXT_LINES: usize = 3;
+/// Ограничение на размер таблицы LCS (строк старой × строк новой версии).
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffKind {
+    Same,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DiffRow {
+    Line {
+        kind: DiffKind,
+        text: String,
+    },
+    /// Пропущенный блок неизменённых строк
+    Gap(usize),
+}
+
+/// Построчный diff (LCS): `old` — выбранная версия, `new` — текущая.
+/// `None`, если документы слишком большие для сравнения в браузере.
+fn diff_lines(old: &str, new: &str) -> Option<Vec<(DiffKind, String)>> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let (n, m) = (a.len(), b.len());
+    if (n + 1).saturating_mul(m + 1) > MAX_DIFF_CELLS {
+        return None;
+    }
+
+    // lcs[i][j] — длина общей подпоследовательности суффиксов a[i..] и b[j..]
+    let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+    let at = |i: usize, j: usize| i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[at(i, j)] = if a[i] == b[j] {
+                lcs[at(i + 1, j + 1)] + 1
+            } else {
+                lcs[at(i + 1, j)].max(lcs[at(i, j + 1)])
+            };
+        }
+    }
+
+    let mut out = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            out.push((DiffKind::Same, a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[at(i + 1, j)] >= lcs[at(i, j + 1)] {
+            out.push((DiffKind::Removed, a[i].to_string()));
+            i += 1;
+        } else {
+            out.push((DiffKind::Added, b[j].to_string()));
+            j += 1;
+        }
+    }
+    out.extend(a[i..].iter().map(|l| (DiffKind::Removed, l.to_string())));
+    out.extend(b[j..].iter().map(|l| (DiffKind::Added, l.to_string())));
+    Some(out)
+}
+
+/// Оставляет изменения с [`DIFF_CONTEXT_LINES`] строками контекста, остальное сворачивает.
+fn collapse_context(lines: Vec<(DiffKind, String)>) -> Vec<DiffRow> {
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (kind, _))| *kind != DiffKind::Same)
+        .map(|(idx, _)| idx)
+        .collect();
+    let visible = |idx: usize| {
+        changed
+            .iter()
+            .any(|&c| idx + DIFF_CONTEXT_LINES >= c && idx <= c + DIFF_CONTEXT_LINES)
+    };
+
+    let mut rows = Vec::new();
+    let mut hidden = 0;
+    for (idx, (kind, text)) in lines.into_iter().enumerate() {
+        if visible(idx) {
+            if hidden > 0 {
+                rows.push(DiffRow::Gap(hidden));
+                hidden = 0;
+            }
+            rows.push(DiffRow::Line { kind, text });
+        } else {
+            hidden += 1;
+        }
+    }
+    if hidden > 0 {
+        rows.push(DiffRow::Gap(hidden));
+    }
+    rows
+}
+
+async fn fetch_versions(raw_base: &str, ref_id: &str) -> Result<Vec<RawPayloadVersionDto>, String> {
+    Ok(api_client::get_json(&format!("{}/{}/versions", raw_base, ref_id)).await?)
+}
+
+async fn fetch_version_json(raw_base: &str, id: &str) -> Result<String, String> {
+    let value: serde_json::Value = api_client::get_json(&format!("{}/{}", raw_base, id)).await?;
+    serde_json::to_string_pretty(&value).map_err(|e| format!("Failed to format JSON: {}", e))
+}
+
+fn version_label(v: &RawPayloadVersionDto) -> String {
+    format!(
+        "v{} · {} · {}{}",
+        v.version,
+        format_datetime_utc_local(&v.fetched_at, "%d.%m.%Y %H:%M:%S"),
+        format_bytes_compact(v.size_bytes),
+        if v.is_current {
+            " · текущая"
+        } else {
+            ""
+        }
+    )
+}
+
+#[component]
+pub fn RawPayloadVersions(
+    /// Базовый путь raw-эндпоинта документа, например `/api/a012/raw`
+    raw_base: &'static str,
+    /// `raw_payload_ref`, на который ссылается документ
+    current_ref: Signal<Option<String>>,
+    /// Уже загруженный (отформатированный) JSON текущей версии
+    current_json: Signal<Option<String>>,
+) -> impl IntoView {
+    let versions = RwSignal::new(Vec::<RawPayloadVersionDto>::new());
+    let selected = RwSignal::new(None::<String>);
+    let selected_json = RwSignal::new(None::<String>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    Effect::new(move |_| {
+        let Some(ref_id) = current_ref.get().filter(|r| !r.is_empty()) else {
+            versions.set(Vec::new());
+            return;
+        };
+        spawn_local(async move {
+            match fetch_versions(raw_base, &ref_id).await {
+                Ok(list) => versions.set(list),
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    });
+
+    let on_select = move |ev: leptos::ev::Event| {
+        let id = event_target_value(&ev);
+        selected_json.set(None);
+        error.set(None);
+        let is_current =
+            versions.with_untracked(|list| list.iter().any(|v| v.id == id && v.is_current));
+        if id.is_empty() || is_current {
+            selected.set(None);
+            return;
+        }
+        selected.set(Some(id.clone()));
+        loading.set(true);
+        spawn_local(async move {
+            match fetch_version_json(raw_base, &id).await {
+                Ok(json) => selected_json.set(Some(json)),
+                Err(e) => error.set(Some(e)),
+            }
+            loading.set(false);
+        });
+    };
+
+    let diff = Memo::new(move |_| {
+        let old = selected_json.get()?;
+        let new = current_json.get()?;
+        Some(diff_lines(&old, &new).map(|lines| {
+            let added = lines.iter().filter(|(k, _)| *k == DiffKind::Added).count();
+            let removed = lines
+                .iter()
+                .filter(|(k, _)| *k == DiffKind::Removed)
+                .count();
+            (added, removed, collapse_context(lines))
+        }))
+    });
+
+    view! {
+        <Show when=move || versions.with(|list| list.len() > 1)>
+            <div class="raw-versions">
+                <div class="raw-versions__toolbar">
+                    <label class="raw-versions__label">"Версия payload"</label>
+                    <select class="raw-versions__select" on:change=on_select>
+                        {move || versions.get().into_iter().rev().map(|v| {
+                            let label = version_label(&v);
+                            let value = if v.is_current { String::new() } else { v.id.clone() };
+                            view! { <option value=value selected=v.is_current>{label}</option> }
+                        }).collect_view()}
+                    </select>
+                    <span class="raw-versions__hint">
+                        {move || format!("Сохранено версий: {}", versions.with(|list| list.len()))}
+                    </span>
+                </div>
+                {move || error.get().map(|e| view! {
+                    <div class="alert alert--error">{format!("Не удалось загрузить версию: {}", e)}</div>
+                })}
+                {move || {
+                    if selected.get().is_none() {
+                        return view! { <></> }.into_any();
+                    }
+                    if loading.get() {
+                        return view! { <Spinner size=SpinnerSize::Tiny /> }.into_any();
+                    }
+                    let summary = match diff.get() {
+                        Some(Some((added, removed, _))) if added + removed == 0 => {
+                            "Отличий от текущей версии нет (совпадает после форматирования)".to_string()
+                        }
+                        Some(Some((added, removed, _))) => {
+                            format!("Отличия от текущей версии: +{} −{} строк", added, removed)
+                        }
+                        Some(None) => "Payload слишком большой для построчного сравнения".to_string(),
+                        None => String::new(),
+                    };
+                    let rows = diff.get().flatten().map(|(_, _, rows)| rows).unwrap_or_default();
+                    view! {
+                        <div class="raw-versions__summary">{summary}</div>
+                        {(!rows.is_empty()).then(|| view! {
+                            <pre class="raw-versions__diff">
+                                {rows.into_iter().map(|row| match row {
+                                    DiffRow::Line { kind, text } => {
+                                        let (class, sign) = match kind {
+                                            DiffKind::Same => ("raw-versions__line", " "),
+                                            DiffKind::Added => ("raw-versions__line raw-versions__line--added", "+"),
+                                            DiffKind::Removed => ("raw-versions__line raw-versions__line--removed", "−"),
+                                        };
+                                        view! { <div class=class>{format!("{} {}", sign, text)}</div> }.into_any()
+                                    }
+                                    DiffRow::Gap(count) => view! {
+                                        <div class="raw-versions__line raw-versions__line--gap">
+                                            {format!("… {} без изменений", count)}
+                                        </div>
+                                    }.into_any(),
+                                }).collect_view()}
+                            </pre>
+                        })}
+                        {selected_json.get().map(|json| view! {
+                            <div style="max-height: calc(100vh - 360px); overflow: auto;">
+                                <JsonViewer json_content=json title="Выбранная версия".to_string() />
+                            </div>
+                        })}
+                    }
+                    .into_any()
+                }}
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,330 @@
+//! `RowContextMenu` — контекстное меню строк списков (правый клик по строке).
+//!
+//! Модуль списка один раз собирает набор [`RowAction`] (открыть, провести, копировать id,
+//! raw JSON, пометить), а строки только открывают меню через [`RowMenuState::open_at`].
+//! Действия с `access` фильтруются по скоупам текущего пользователя, так что меню
+//! не предлагает того, что сервер всё равно отклонит.
+//!
+//! # Использование
+//!
+//! ```rust
+//! let row_menu = RowMenuState::new();
+//! let row_actions = vec![
+//!     RowAction::new("Открыть", "eye", move |row| open_detail(row.id, row.title)),
+//!     RowAction::copy_id(),
+//! ];
+//!
+//! <TableRow on:contextmenu=move |ev| row_menu.open_at(&ev, RowRef::new(id.clone(), title.clone()))>
+//! ...
+//! <RowContextMenu state=row_menu actions=row_actions />
+//! ```
+
+use std::sync::Arc;
+
+use contracts::system::favorites::{FavoriteUpsertRequest, FAVORITE_COLOR_YELLOW};
+use gloo_net::http::Request;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::shared::api_utils::api_base;
+use crate::shared::clipboard::copy_to_clipboard;
+use crate::shared::icons::icon;
+use crate::shared::json_viewer::JsonViewer;
+use crate::shared::modal_stack::ModalStackService;
+use crate::system::auth::context::{has_read_access, has_write_access, use_auth};
+use crate::system::auth::storage;
+
+/// Строка, для которой открыто меню.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowRef {
+    pub id: String,
+    /// Человекочитаемое обозначение (номер документа) — для заголовков вкладок и избранного.
+    pub title: String,
+}
+
+impl RowRef {
+    pub fn new(id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+        }
+    }
+}
+
+/// Какой доступ нужен для действия.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowActionAccess {
+    Any,
+    Read(&'static str),
+    /// Изменяющие действия (проведение и т.п.) — режим скоупа `all`.
+    Write(&'static str),
+}
+
+/// Действие контекстного меню строки.
+#[derive(Clone)]
+pub struct RowAction {
+    pub label: &'static str,
+    pub icon: &'static str,
+    pub access: RowActionAccess,
+    run: Arc<dyn Fn(RowRef) + Send + Sync>,
+}
+
+impl RowAction {
+    pub fn new(
+        label: &'static str,
+        icon: &'static str,
+        run: impl Fn(RowRef) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            label,
+            icon,
+            access: RowActionAccess::Any,
+            run: Arc::new(run),
+        }
+    }
+
+    pub fn read(mut self, scope_id: &'static str) -> Self {
+        self.access = RowActionAccess::Read(scope_id);
+        self
+    }
+
+    pub fn write(mut self, scope_id: &'static str) -> Self {
+        self.access = RowActionAccess::Write(scope_id);
+        self
+    }
+
+    /// Скопировать id строки в буфер обмена.
+    pub fn copy_id() -> Self {
+        Self::new("Копировать ID", "copy", |row| {
+            copy_to_clipboard(&row.id)
+        })
+    }
+
+    /// POST без тела на URL строки (провести / отменить проведение), затем `on_done`.
+    pub fn post_to(
+        label: &'static str,
+        icon: &'static str,
+        url: fn(&str) -> String,
+        on_done: impl Fn() + Copy + Send + Sync + 'static,
+    ) -> Self {
+        Self::new(label, icon, move |row| {
+            let url = url(&row.id);
+            spawn_local(async move {
+                let mut request = Request::post(&url);
+                if let Some(token) = storage::get_access_token() {
+                    request = request.header("Authorization", &format!("Bearer {}", token));
+                }
+                match request.send().await {
+                    Ok(resp) if resp.ok() => {}
+                    Ok(resp) => alert(&format!("{}: HTTP {}", label, resp.status())),
+                    Err(err) => alert(&format!("{}: {}", label, err)),
+                }
+                on_done();
+            });
+        })
+    }
+
+    /// Показать исходный JSON маркетплейса в модальном окне.
+    /// Вызывать в теле компонента: берёт `ModalStackService` из контекста.
+    pub fn raw_json(source: fn(&str) -> RawJsonSource) -> Self {
+        let modal_stack = use_context::<ModalStackService>();
+        Self::new("Raw JSON", "code", move |row| {
+            let Some(modal_stack) = modal_stack else {
+                return;
+            };
+            let src = source(&row.id);
+            spawn_local(async move {
+                match load_raw_json(src).await {
+                    Ok(json) => {
+                        let title = format!("Raw JSON: {}", row.title);
+                        modal_stack.push_with_frame(
+                            Some("max-width: min(1100px, 95vw); width: min(1100px, 95vw);".to_string()),
+                            None,
+                            move |_| {
+                                view! { <JsonViewer json_content=json.clone() title=title.clone() /> }
+                                    .into_any()
+                            },
+                        );
+                    }
+                    Err(err) => alert(&format!("Не удалось загрузить raw JSON: {}", err)),
+                }
+            });
+        })
+    }
+
+    /// Пометить строку: добавить в избранное (жёлтая метка).
+    /// `target_kind` — вид цели избранного, вкладка — `{tab_prefix}{id}`.
+    pub fn favorite(target_kind: &'static str, tab_prefix: &'static str) -> Self {
+        Self::new("Пометить", "tag", move |row| {
+            let req = FavoriteUpsertRequest {
+                target_kind: target_kind.to_string(),
+                target_id: row.id.clone(),
+                target_title: row.title.clone(),
+                tab_key: format!("{}{}", tab_prefix, row.id),
+                color: FAVORITE_COLOR_YELLOW.to_string(),
+                comment: None,
+                is_global: false,
+            };
+            spawn_local(async move {
+                if let Err(err) = crate::system::favorites::api::upsert_favorite(req).await {
+                    alert(&format!("Не удалось пометить: {}", err));
+                }
+            });
+        })
+    }
+}
+
+/// Откуда брать raw JSON строки.
+pub enum RawJsonSource {
+    /// URL сразу отдаёт JSON.
+    Direct(String),
+    /// Документ с `source_meta.raw_payload_ref`; JSON — по `{raw_url_prefix}/{ref}`.
+    ViaDocument {
+        document_url: String,
+        raw_url_prefix: String,
+    },
+}
+
+async fn fetch_text(url: &str) -> Result<String, String> {
+    let mut request = Request::get(url);
+    if let Some(token) = storage::get_access_token() {
+        request = request.header("Authorization", &format!("Bearer {}", token));
+    }
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    response.text().await.map_err(|e| e.to_string())
+}
+
+async fn load_raw_json(source: RawJsonSource) -> Result<String, String> {
+    let url = match source {
+        RawJsonSource::Direct(url) => url,
+        RawJsonSource::ViaDocument {
+            document_url,
+            raw_url_prefix,
+        } => {
+            let doc: serde_json::Value = serde_json::from_str(&fetch_text(&document_url).await?)
+                .map_err(|e| e.to_string())?;
+            let raw_ref = doc["source_meta"]["raw_payload_ref"]
+                .as_str()
+                .filter(|r| !r.is_empty())
+                .ok_or_else(|| "у документа нет raw_payload_ref".to_string())?;
+            format!("{}/{}", raw_url_prefix, raw_ref)
+        }
+    };
+    fetch_text(&url).await
+}
+
+/// URL API относительно `api_base()` — для [`RawJsonSource`].
+pub fn api_url(path: &str) -> String {
+    format!("{}{}", api_base(), path)
+}
+
+fn alert(message: &str) {
+    if let Some(window) = web_sys::window() {
+        let _ = window.alert_with_message(message);
+    }
+}
+
+/// Состояние меню одного списка: открыто ли, где и для какой строки.
+#[derive(Clone, Copy)]
+pub struct RowMenuState {
+    row: RwSignal<Option<RowRef>>,
+    pos: RwSignal<(f64, f64)>,
+}
+
+impl RowMenuState {
+    pub fn new() -> Self {
+        Self {
+            row: RwSignal::new(None),
+            pos: RwSignal::new((0.0, 0.0)),
+        }
+    }
+
+    /// Обработчик `contextmenu` строки: открыть меню у курсора.
+    pub fn open_at(&self, ev: &web_sys::MouseEvent, row: RowRef) {
+        ev.prevent_default();
+        self.pos.set((ev.client_x() as f64, ev.client_y() as f64));
+        self.row.set(Some(row));
+    }
+
+    pub fn close(&self) {
+        self.row.set(None);
+    }
+}
+
+impl Default for RowMenuState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn allowed(access: RowActionAccess) -> bool {
+    let (auth_state, _) = use_auth();
+    match access {
+        RowActionAccess::Any => true,
+        RowActionAccess::Read(scope_id) => has_read_access(auth_state, scope_id),
+        RowActionAccess::Write(scope_id) => has_write_access(auth_state, scope_id),
+    }
+}
+
+/// Контекстное меню строк списка; рендерится один раз на список.
+#[component]
+pub fn RowContextMenu(state: RowMenuState, actions: Vec<RowAction>) -> impl IntoView {
+    let actions: Vec<RowAction> = actions.into_iter().filter(|a| allowed(a.access)).collect();
+    let actions = StoredValue::new(actions);
+
+    view! {
+        <Show when=move || state.row.with(|r| r.is_some())>
+            <button
+                type="button"
+                style="position: fixed; inset: 0; z-index: 10000; background: transparent; border: none; cursor: default;"
+                aria-label="Закрыть меню"
+                on:click=move |_| state.close()
+                on:contextmenu=move |ev| {
+                    ev.prevent_default();
+                    state.close();
+                }
+            />
+            <div
+                style=move || {
+                    let (x, y) = state.pos.get();
+                    format!(
+                        "position: fixed; left: {x:.1}px; top: {y:.1}px; z-index: 10001; \
+                         min-width: 210px; background: var(--color-menu-surface, var(--color-surface)); \
+                         border: 1px solid var(--color-border); border-radius: var(--radius-md); \
+                         box-shadow: 0 8px 28px rgba(0,0,0,.32); \
+                         padding: var(--spacing-xs) 0; \
+                         display: flex; flex-direction: column; gap: 2px; \
+                         animation: fadeIn 0.15s ease;"
+                    )
+                }
+            >
+                {move || {
+                    actions
+                        .get_value()
+                        .into_iter()
+                        .map(|action| {
+                            let run = action.run.clone();
+                            view! {
+                                <button
+                                    class="theme-dropdown__item"
+                                    on:click=move |_| {
+                                        if let Some(row) = state.row.get_untracked() {
+                                            state.close();
+                                            run(row);
+                                        }
+                                    }
+                                >
+                                    {icon(action.icon)}
+                                    {action.label}
+                                </button>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,18 @@
+//! Бейдж «Песочница» в шапке документа, загруженного через подключение
+//! в режиме песочницы (`is_sandbox` агрегата).
+
+use leptos::prelude::*;
+
+#[component]
+pub fn SandboxBadge(#[prop(into)] is_sandbox: Signal<bool>) -> impl IntoView {
+    view! {
+        <Show when=move || is_sandbox.get()>
+            <span
+                class="badge badge--warning"
+                title="Тестовые данные из песочницы: не проводятся пакетно и при перепроведении за период"
+            >
+                "Песочница"
+            </span>
+        </Show>
+    }
+}
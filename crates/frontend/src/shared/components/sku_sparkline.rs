@@ -0,0 +1,101 @@
+//! Мини-график продаж товара МП (a007) за 30 дней для карточек документов.
+//!
+//! Данные — `GET /api/analytics/sku/{id}/sparkline` (агрегаты p900 по дням).
+//! Окно заканчивается датой документа, поэтому по графику видно, типична ли
+//! продажа для товара, без перехода в аналитику.
+
+use crate::shared::api_utils::api_base;
+use crate::shared::bi_card::points_to_svg_path;
+use crate::shared::money_format::{format_number, format_qty};
+use contracts::projections::p900_mp_sales_register::SkuSparklineResponse;
+use gloo_net::http::Request;
+use leptos::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+const SPARKLINE_DAYS: u32 = 30;
+
+async fn fetch_sku_sparkline(
+    marketplace_product_ref: &str,
+    date_to: Option<&str>,
+) -> Result<SkuSparklineResponse, String> {
+    let mut url = format!(
+        "{}/api/analytics/sku/{}/sparkline?days={}",
+        api_base(),
+        urlencoding::encode(marketplace_product_ref),
+        SPARKLINE_DAYS
+    );
+    if let Some(date_to) = date_to {
+        url.push_str(&format!("&date_to={}", date_to));
+    }
+
+    let response = Request::get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch: {}", e))?;
+    if response.status() != 200 {
+        return Err(format!("Server error: {}", response.status()));
+    }
+    response
+        .json::<SkuSparklineResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse: {}", e))
+}
+
+/// Sparkline продаж (шт. в день) с подписью «итого / в среднем за день».
+///
+/// `date_to` — дата документа (`YYYY-MM-DD` или ISO datetime); без неё окно
+/// заканчивается сегодня.
+#[component]
+pub fn SkuSparkline(
+    marketplace_product_ref: String,
+    #[prop(optional, into)] date_to: Option<String>,
+) -> impl IntoView {
+    let (data, set_data) = signal(None::<Result<SkuSparklineResponse, String>>);
+
+    let date_to = date_to.map(|value| value.chars().take(10).collect::<String>());
+    spawn_local(async move {
+        let result = fetch_sku_sparkline(&marketplace_product_ref, date_to.as_deref()).await;
+        set_data.set(Some(result));
+    });
+
+    view! {
+        <div class="sku-sparkline" style="display: flex; align-items: center; gap: var(--spacing-sm); margin-top: var(--spacing-xs); min-height: 24px;">
+            {move || match data.get() {
+                None => view! {
+                    <span style="color: var(--color-text-secondary); font-size: var(--font-size-xs);">"Загрузка продаж…"</span>
+                }.into_any(),
+                Some(Err(_)) => view! {
+                    <span style="color: var(--color-text-secondary); font-size: var(--font-size-xs);">"Продажи недоступны"</span>
+                }.into_any(),
+                Some(Ok(sparkline)) => {
+                    let qty: Vec<f64> = sparkline.points.iter().map(|p| p.qty).collect();
+                    let (line_d, fill_d) = points_to_svg_path(&qty);
+                    let title = sparkline
+                        .points
+                        .iter()
+                        .map(|p| format!("{}: {}", p.date, format_qty(p.qty)))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    let caption = format!(
+                        "{} дн.: {} шт., в среднем {} в день",
+                        sparkline.points.len(),
+                        format_qty(sparkline.total_qty),
+                        format_number(sparkline.avg_daily_qty, 1)
+                    );
+                    view! {
+                        <svg
+                            viewBox="0 0 100 30"
+                            preserveAspectRatio="none"
+                            style="width: 120px; height: 24px; flex-shrink: 0;"
+                        >
+                            <title>{title}</title>
+                            <path d=fill_d fill="var(--color-primary)" opacity="0.15" stroke="none" />
+                            <path d=line_d fill="none" stroke="var(--color-primary)" stroke-width="1.5" vector-effect="non-scaling-stroke" />
+                        </svg>
+                        <span style="color: var(--color-text-secondary); font-size: var(--font-size-xs);">{caption}</span>
+                    }.into_any()
+                }
+            }}
+        </div>
+    }
+}
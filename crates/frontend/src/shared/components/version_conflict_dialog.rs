@@ -0,0 +1,67 @@
+//! VersionConflictDialog — «документ изменён другим пользователем».
+//!
+//! Показывается, когда команда над документом (проведение, отмена проведения)
+//! вернула 409 с [`VersionConflict`]: документ успели изменить после загрузки.
+//! Пользователь либо перезагружает документ, либо закрывает диалог.
+//!
+//! ```rust
+//! <VersionConflictDialog conflict=vm.version_conflict on_reload=on_reload />
+//! ```
+
+use contracts::domain::common::VersionConflict;
+use leptos::prelude::*;
+use thaw::*;
+
+/// Диалог открыт, пока `conflict` содержит значение; закрытие сбрасывает его в `None`.
+#[component]
+pub fn VersionConflictDialog(
+    conflict: RwSignal<Option<VersionConflict>>,
+    #[prop(into)] on_reload: Callback<()>,
+) -> impl IntoView {
+    let open = RwSignal::new(false);
+
+    Effect::new(move |_| {
+        let has_conflict = conflict.with(|c| c.is_some());
+        if open.get_untracked() != has_conflict {
+            open.set(has_conflict);
+        }
+    });
+    Effect::new(move |_| {
+        if !open.get() && conflict.with_untracked(|c| c.is_some()) {
+            conflict.set(None);
+        }
+    });
+
+    view! {
+        <Dialog open=open>
+            <DialogSurface>
+                <DialogBody>
+                    <DialogTitle>"Документ изменён другим пользователем"</DialogTitle>
+                    <DialogContent>
+                        <div>"Пока документ был открыт, его изменили. Команда не выполнена."</div>
+                        {move || conflict.get().map(|c| view! {
+                            <div style="margin-top: var(--spacing-sm); color: var(--color-text-secondary); font-size: var(--font-size-sm);">
+                                {format!("Версия на экране: {}, текущая: {}", c.expected_version, c.current_version)}
+                            </div>
+                        })}
+                        <div style="margin-top: var(--spacing-sm);">"Перезагрузить документ?"</div>
+                    </DialogContent>
+                    <DialogActions>
+                        <Button appearance=ButtonAppearance::Secondary on_click=move |_| open.set(false)>
+                            "Отмена"
+                        </Button>
+                        <Button
+                            appearance=ButtonAppearance::Primary
+                            on_click=move |_| {
+                                open.set(false);
+                                on_reload.run(());
+                            }
+                        >
+                            "Перезагрузить"
+                        </Button>
+                    </DialogActions>
+                </DialogBody>
+            </DialogSurface>
+        </Dialog>
+    }
+}
@@ -184,6 +184,20 @@ pub fn format_bytes_compact(n: u64) -> String {
     }
 }
 
+/// Число HTTP-запросов с пометкой о повторах: «12 (повторов: 3)», «—» если ничего не было.
+pub fn format_http_calls(request_count: i64, retry_count: i64) -> String {
+    let calls = if request_count > 0 {
+        request_count.to_string()
+    } else {
+        "—".to_string()
+    };
+    if retry_count > 0 {
+        format!("{} (повторов: {})", calls, retry_count)
+    } else {
+        calls
+    }
+}
+
 /// Строка «↑{up} ↓{down}» из байт (пусто если оба 0).
 pub fn format_http_traffic(bytes_sent: i64, bytes_received: i64) -> Option<String> {
     let up = bytes_sent.max(0) as u64;
@@ -0,0 +1,116 @@
+//! Дельта-обновление открытых списков (`contracts::shared::delta`).
+//!
+//! Вместо перезагрузки страницы список запрашивает изменения с момента прошлого
+//! ответа (`updated_after=<server_time>`) и вливает изменённые строки на место.
+//! Если изменения затрагивают состав страницы (новые строки, другое общее
+//! число строк, обрезанная дельта) — список перезагружается целиком.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use gloo_net::http::Request;
+use gloo_timers::future::TimeoutFuture;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use serde::de::DeserializeOwned;
+
+/// Строка списка с идентификатором документа.
+pub trait DeltaRow {
+    fn row_id(&self) -> &str;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeltaOutcome {
+    /// Изменения влиты в текущие строки.
+    Merged,
+    /// Состав страницы изменился — нужна полная загрузка.
+    Reload,
+}
+
+/// Добавить `updated_after` к URL списка.
+pub fn delta_url(list_url: &str, server_time: &str) -> String {
+    let sep = if list_url.contains('?') { '&' } else { '?' };
+    format!(
+        "{}{}updated_after={}",
+        list_url,
+        sep,
+        urlencoding::encode(server_time)
+    )
+}
+
+/// Запросить дельту. `Ok(None)` — сервер ответил `304 Not Modified`.
+pub async fn fetch_delta<T: DeserializeOwned>(url: &str) -> Result<Option<T>, String> {
+    let response = Request::get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch delta: {}", e))?;
+    match response.status() {
+        304 => Ok(None),
+        200 => response
+            .json::<T>()
+            .await
+            .map(Some)
+            .map_err(|e| format!("Failed to parse delta: {}", e)),
+        status => Err(format!("Server error: {}", status)),
+    }
+}
+
+/// Влить изменённые строки в текущую страницу.
+///
+/// `total_changed` — общее число строк выборки стало другим (добавились или
+/// ушли строки): порядок и состав страниц сдвинулись, вливать нельзя.
+pub fn merge_rows<T: DeltaRow>(
+    rows: &mut Vec<T>,
+    changed: Vec<T>,
+    deleted_ids: &[String],
+    total_changed: bool,
+    truncated: bool,
+) -> DeltaOutcome {
+    if total_changed || truncated {
+        return DeltaOutcome::Reload;
+    }
+    if rows
+        .iter()
+        .any(|r| deleted_ids.iter().any(|id| id == r.row_id()))
+    {
+        return DeltaOutcome::Reload;
+    }
+    let mut updates = Vec::with_capacity(changed.len());
+    for row in changed {
+        match rows.iter().position(|r| r.row_id() == row.row_id()) {
+            Some(index) => updates.push((index, row)),
+            // Строка вошла в эту страницу (сменилась сортировка/фильтр по полю)
+            None => return DeltaOutcome::Reload,
+        }
+    }
+    for (index, row) in updates {
+        rows[index] = row;
+    }
+    DeltaOutcome::Merged
+}
+
+/// Периодически вызывать `tick`, пока жив владелец (вкладка списка открыта).
+///
+/// `is_active` позволяет пропускать тики, пока вкладка не на переднем плане.
+pub fn spawn_delta_polling(
+    interval_ms: u32,
+    is_active: impl Fn() -> bool + 'static,
+    tick: impl Fn() + 'static,
+) {
+    let alive = Arc::new(AtomicBool::new(true));
+    on_cleanup({
+        let alive = alive.clone();
+        move || alive.store(false, Ordering::Relaxed)
+    });
+    spawn_local(async move {
+        loop {
+            TimeoutFuture::new(interval_ms).await;
+            if !alive.load(Ordering::Relaxed) {
+                break;
+            }
+            if is_active() {
+                tick();
+            }
+        }
+    });
+}
@@ -0,0 +1,113 @@
+//! Всплывающие уведомления о серверных ошибках с кодом запроса.
+//!
+//! [`api_client`](crate::shared::api_client) сообщает сюда о каждом ответе 5xx;
+//! пользователь видит текст ошибки и код запроса (`x-request-id`), который
+//! можно скопировать и передать в поддержку — по нему ищется запись в логах.
+//!
+//! ```rust
+//! view! { <ErrorToastHost /> }
+//! ```
+
+use std::cell::{Cell, RefCell};
+
+use gloo_timers::future::TimeoutFuture;
+use leptos::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+use crate::shared::clipboard::copy_to_clipboard;
+
+/// Сколько уведомление висит на экране без действий пользователя.
+const TOAST_LIFETIME_MS: u32 = 10_000;
+/// Больше уведомлений одновременно не показываем — старые вытесняются.
+const MAX_TOASTS: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ErrorToast {
+    id: u64,
+    message: String,
+    request_id: Option<String>,
+}
+
+thread_local! {
+    static TOASTS: RefCell<Option<RwSignal<Vec<ErrorToast>>>> = const { RefCell::new(None) };
+    static NEXT_ID: Cell<u64> = const { Cell::new(1) };
+}
+
+fn dismiss(toasts: RwSignal<Vec<ErrorToast>>, id: u64) {
+    toasts.update(|list| list.retain(|t| t.id != id));
+}
+
+/// Показывает уведомление об ошибке; без смонтированного [`ErrorToastHost`] ничего не делает.
+pub fn push_error(message: impl Into<String>, request_id: Option<String>) {
+    let Some(toasts) = TOASTS.with(|t| *t.borrow()) else {
+        return;
+    };
+    let id = NEXT_ID.with(|n| {
+        let id = n.get();
+        n.set(id + 1);
+        id
+    });
+    toasts.update(|list| {
+        list.push(ErrorToast {
+            id,
+            message: message.into(),
+            request_id,
+        });
+        if list.len() > MAX_TOASTS {
+            list.remove(0);
+        }
+    });
+    spawn_local(async move {
+        TimeoutFuture::new(TOAST_LIFETIME_MS).await;
+        dismiss(toasts, id);
+    });
+}
+
+/// Контейнер уведомлений; монтируется один раз в корне приложения.
+#[component]
+pub fn ErrorToastHost() -> impl IntoView {
+    let toasts = RwSignal::new(Vec::<ErrorToast>::new());
+    TOASTS.with(|t| *t.borrow_mut() = Some(toasts));
+    on_cleanup(|| TOASTS.with(|t| *t.borrow_mut() = None));
+
+    view! {
+        <div class="error-toasts">
+            <For
+                each=move || toasts.get()
+                key=|toast| toast.id
+                children=move |toast| {
+                    let id = toast.id;
+                    let request_id = toast.request_id.clone();
+                    view! {
+                        <div class="error-toast" role="alert">
+                            <div class="error-toast__message">{toast.message.clone()}</div>
+                            {request_id.map(|rid| {
+                                let copy_value = rid.clone();
+                                view! {
+                                    <div class="error-toast__id">
+                                        "Код ошибки: "
+                                        <code>{rid}</code>
+                                        <button
+                                            class="error-toast__copy"
+                                            title="Скопировать код ошибки"
+                                            on:click=move |_| copy_to_clipboard(&copy_value)
+                                        >
+                                            "Копировать"
+                                        </button>
+                                    </div>
+                                }
+                            })}
+                            <button
+                                class="error-toast__close"
+                                title="Закрыть"
+                                on:click=move |_| dismiss(toasts, id)
+                            >
+                                "×"
+                            </button>
+                        </div>
+                    }
+                }
+            />
+        </div>
+    }
+}
@@ -1,4 +1,5 @@
 /// Универсальный модуль для экспорта данных в Excel/CSV формат
+use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
 
@@ -11,6 +12,49 @@ pub trait ExcelExportable {
     fn to_csv_row(&self) -> Vec<String>;
 }
 
+/// JS binding для записи XLSX через SheetJS (`window.exportXlsxFile` в index.html)
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = exportXlsxFile, catch)]
+    fn export_xlsx_file(
+        rows: &js_sys::Array,
+        sheet_name: &str,
+        filename: &str,
+    ) -> Result<(), JsValue>;
+}
+
+/// Ячейка XLSX: числа пишутся числами, чтобы в Excel работали формулы и итоги
+pub enum XlsxCell {
+    Text(String),
+    Number(f64),
+}
+
+/// Экспортирует таблицу (первая строка — заголовки) в XLSX файл и инициирует скачивание
+pub fn export_rows_to_xlsx(
+    rows: &[Vec<XlsxCell>],
+    sheet_name: &str,
+    filename: &str,
+) -> Result<(), String> {
+    if rows.len() <= 1 {
+        return Err("Нет данных для экспорта".to_string());
+    }
+
+    let js_rows = js_sys::Array::new();
+    for row in rows {
+        let js_row = js_sys::Array::new();
+        for cell in row {
+            match cell {
+                XlsxCell::Text(value) => js_row.push(&JsValue::from_str(value)),
+                XlsxCell::Number(value) => js_row.push(&JsValue::from_f64(*value)),
+            };
+        }
+        js_rows.push(&js_row);
+    }
+
+    export_xlsx_file(&js_rows, sheet_name, filename)
+        .map_err(|e| format!("Ошибка записи XLSX: {:?}", e))
+}
+
 /// Экспортирует список данных в CSV файл и инициирует скачивание
 pub fn export_to_excel<T: ExcelExportable>(data: &[T], filename: &str) -> Result<(), String> {
     if data.is_empty() {
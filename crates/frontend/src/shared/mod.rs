@@ -1,3 +1,4 @@
+pub mod api_client;
 pub mod api_utils;
 pub mod auth_download;
 pub mod bi_card;
@@ -8,8 +9,10 @@ pub mod code_format;
 pub mod components;
 pub mod data;
 pub mod date_utils;
+pub mod delta_sync;
 pub mod dom_validator;
 pub mod drilldown_report;
+pub mod error_toasts;
 pub mod excel_importer;
 pub mod export;
 pub mod filters;
@@ -19,6 +22,7 @@ pub mod json_viewer;
 pub mod knowledge_base;
 pub mod list_utils;
 pub mod markdown;
+pub mod money_format;
 pub mod modal_frame;
 pub mod modal_stack;
 pub mod page_frame;
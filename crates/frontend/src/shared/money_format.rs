@@ -0,0 +1,217 @@
+//! Единое форматирование сумм и процентов в карточках документов.
+//!
+//! Разделители зависят от языка браузера (`navigator.language`): для `ru*` —
+//! «1 234,56 ₽» и «12,5 %», для остальных — «₽1,234.56» и «12.5%». Пустые
+//! значения выводятся как «—». Отрицательные суммы подсвечиваются классом
+//! `text-negative` через [`amount_class`].
+//!
+//! Это единственная реализация форматирования чисел: табличные хелперы
+//! `components::table::number_format` делегируют сюда.
+
+use std::cell::Cell;
+
+/// Неразрывный пробел: разделитель разрядов и отступ перед «₽»/«%» в ru.
+const NBSP: char = '\u{a0}';
+const EMPTY: &str = "—";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberLocale {
+    Ru,
+    En,
+}
+
+thread_local! {
+    static CURRENT: Cell<Option<NumberLocale>> = const { Cell::new(None) };
+}
+
+impl NumberLocale {
+    /// Локаль пользователя; определяется один раз за сессию.
+    pub fn current() -> Self {
+        CURRENT.with(|cell| {
+            if let Some(locale) = cell.get() {
+                return locale;
+            }
+            let locale = web_sys::window()
+                .and_then(|w| w.navigator().language())
+                .map(|tag| Self::from_language_tag(&tag))
+                .unwrap_or(NumberLocale::Ru);
+            cell.set(Some(locale));
+            locale
+        })
+    }
+
+    pub fn from_language_tag(tag: &str) -> Self {
+        if tag.to_ascii_lowercase().starts_with("ru") {
+            NumberLocale::Ru
+        } else {
+            NumberLocale::En
+        }
+    }
+
+    fn group_separator(self) -> char {
+        match self {
+            NumberLocale::Ru => NBSP,
+            NumberLocale::En => ',',
+        }
+    }
+
+    fn decimal_separator(self) -> char {
+        match self {
+            NumberLocale::Ru => ',',
+            NumberLocale::En => '.',
+        }
+    }
+
+    /// Число с разделителями разрядов и `decimals` знаками после запятой.
+    pub fn number(self, value: f64, decimals: usize) -> String {
+        let fixed = format!("{:.*}", decimals, value);
+        let (sign, unsigned) = match fixed.strip_prefix('-') {
+            // «-0,00» после округления показываем как «0,00»
+            Some(rest) if rest.chars().any(|c| c.is_ascii_digit() && c != '0') => ("-", rest),
+            Some(rest) => ("", rest),
+            None => ("", fixed.as_str()),
+        };
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((i, f)) => (i, Some(f)),
+            None => (unsigned, None),
+        };
+
+        let mut result = String::with_capacity(fixed.len() + integer.len() / 3 + 1);
+        result.push_str(sign);
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                result.push(self.group_separator());
+            }
+            result.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            result.push(self.decimal_separator());
+            result.push_str(fraction);
+        }
+        result
+    }
+
+    /// Сумма в рублях с символом валюты.
+    pub fn money_rub(self, value: f64) -> String {
+        let number = self.number(value, 2);
+        match self {
+            NumberLocale::Ru => format!("{number}{NBSP}₽"),
+            NumberLocale::En => match number.strip_prefix('-') {
+                Some(rest) => format!("-₽{rest}"),
+                None => format!("₽{number}"),
+            },
+        }
+    }
+
+    /// Процент; `value` уже в процентах (12.5 → «12,5 %»).
+    pub fn percent(self, value: f64, decimals: usize) -> String {
+        let number = self.number(value, decimals);
+        match self {
+            NumberLocale::Ru => format!("{number}{NBSP}%"),
+            NumberLocale::En => format!("{number}%"),
+        }
+    }
+}
+
+/// Число в локали пользователя (для колонок, где единица указана в заголовке).
+pub fn format_number(value: f64, decimals: usize) -> String {
+    NumberLocale::current().number(value, decimals)
+}
+
+/// Сумма с двумя знаками без символа валюты (для таблиц с рублёвыми колонками).
+pub fn format_money(value: f64) -> String {
+    NumberLocale::current().number(value, 2)
+}
+
+/// [`format_money`] для необязательных полей.
+pub fn format_money_opt(value: Option<f64>) -> String {
+    value.map(format_money).unwrap_or_else(|| EMPTY.to_string())
+}
+
+/// Сумма с двумя знаками и символом «₽».
+pub fn format_money_rub(value: f64) -> String {
+    NumberLocale::current().money_rub(value)
+}
+
+/// [`format_money_rub`] для необязательных полей.
+pub fn format_money_rub_opt(value: Option<f64>) -> String {
+    value
+        .map(format_money_rub)
+        .unwrap_or_else(|| EMPTY.to_string())
+}
+
+/// Количество: целые без дробной части, иначе до трёх знаков.
+pub fn format_qty(value: f64) -> String {
+    let decimals = if value.fract() == 0.0 { 0 } else { 3 };
+    NumberLocale::current().number(value, decimals)
+}
+
+/// Процент из значения в процентах (commission_percent = 12.5).
+pub fn format_percent(value: f64, decimals: usize) -> String {
+    NumberLocale::current().percent(value, decimals)
+}
+
+/// [`format_percent`] для необязательных полей.
+pub fn format_percent_opt(value: Option<f64>, decimals: usize) -> String {
+    value
+        .map(|v| format_percent(v, decimals))
+        .unwrap_or_else(|| EMPTY.to_string())
+}
+
+/// Процент из доли (ratio = 0.125 → «12,5 %»).
+pub fn format_ratio_percent(ratio: f64, decimals: usize) -> String {
+    format_percent(ratio * 100.0, decimals)
+}
+
+/// CSS-класс для суммы: отрицательные (после округления до копеек) подсвечиваются.
+pub fn amount_class(value: f64) -> &'static str {
+    if value <= -0.005 {
+        "text-negative"
+    } else {
+        ""
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_uses_locale_separators() {
+        assert_eq!(
+            NumberLocale::Ru.number(1234567.891, 2),
+            "1\u{a0}234\u{a0}567,89"
+        );
+        assert_eq!(NumberLocale::En.number(1234567.891, 2), "1,234,567.89");
+        assert_eq!(NumberLocale::En.number(-1234.6, 0), "-1,235");
+        assert_eq!(NumberLocale::En.number(999.0, 2), "999.00");
+    }
+
+    #[test]
+    fn number_rounds_to_requested_decimals() {
+        assert_eq!(NumberLocale::Ru.number(1234.567, 0), "1\u{a0}235");
+        assert_eq!(NumberLocale::Ru.number(1234.567, 1), "1\u{a0}234,6");
+        assert_eq!(NumberLocale::Ru.number(1234.567, 3), "1\u{a0}234,567");
+        assert_eq!(NumberLocale::Ru.number(-1234.0, 0), "-1\u{a0}234");
+        assert_eq!(NumberLocale::Ru.number(0.0, 2), "0,00");
+    }
+
+    #[test]
+    fn rounded_negative_zero_has_no_sign() {
+        assert_eq!(NumberLocale::En.number(-0.001, 2), "0.00");
+    }
+
+    #[test]
+    fn money_and_percent_place_symbols_by_locale() {
+        assert_eq!(NumberLocale::Ru.money_rub(-1500.0), "-1\u{a0}500,00\u{a0}₽");
+        assert_eq!(NumberLocale::En.money_rub(-1500.0), "-₽1,500.00");
+        assert_eq!(NumberLocale::Ru.percent(12.5, 1), "12,5\u{a0}%");
+        assert_eq!(NumberLocale::En.percent(12.5, 1), "12.5%");
+    }
+
+    #[test]
+    fn language_tag_detection() {
+        assert_eq!(NumberLocale::from_language_tag("ru-RU"), NumberLocale::Ru);
+        assert_eq!(NumberLocale::from_language_tag("en-US"), NumberLocale::En);
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! group_key строки drilldown по регистратору = "{registrator_type}~~{ref}".
 //! Здесь — разбор ключа и сопоставление типа регистратора с ключом вкладки
-//! детального документа. Используется страницами drilldown (GL и DataView).
+//! детального документа. Используется страницами drilldown (GL и DataView)
+//! и ссылками на документ-источник в списках проекций P900/P904.
 
 /// Разбирает "{type}~~{ref}" на (type, ref). Если разделителя нет — ("", key).
 pub fn split_group_key(group_key: &str) -> (&str, &str) {
@@ -20,6 +21,9 @@ pub fn is_registrator_key(group_key: &str) -> bool {
 /// Человекочитаемое имя типа регистратора (для подписи вкладки/фолбэка).
 pub fn reg_type_name(reg_type: &str) -> &'static str {
     match reg_type {
+        "a009_ozon_returns" => "OZON Возврат",
+        "a010_ozon_fbs_posting" => "OZON FBS",
+        "a011_ozon_fbo_posting" => "OZON FBO",
         "a012_wb_sales" => "WB Продажа",
         "a013_ym_order" => "YM Заказ",
         "a014_ozon_transactions" => "OZON Транзакция",
@@ -41,6 +45,9 @@ pub fn reg_type_name(reg_type: &str) -> &'static str {
 pub fn reg_tab_key(reg_type: &str, reg_ref: &str) -> Option<String> {
     let id = reg_ref;
     match reg_type {
+        "a009_ozon_returns" => Some(format!("a009_ozon_returns_details_{id}")),
+        "a010_ozon_fbs_posting" => Some(format!("a010_ozon_fbs_posting_details_{id}")),
+        "a011_ozon_fbo_posting" => Some(format!("a011_ozon_fbo_posting_details_{id}")),
         "a012_wb_sales" => Some(format!("a012_wb_sales_details_{id}")),
         "a013_ym_order" => Some(format!("a013_ym_order_details_{id}")),
         "a014_ozon_transactions" => Some(format!("a014_ozon_transactions_details_{id}")),
@@ -67,6 +67,7 @@ pub fn role_label(code: &str) -> &'static str {
         "manager" => "Руководитель",
         "operator" => "Оператор",
         "viewer" => "Наблюдатель",
+        "warehouse" => "Склад",
         _ => "Неизвестная роль",
     }
 }
@@ -0,0 +1,53 @@
+use contracts::system::announcements::{
+    AnnouncementDto, AnnouncementFeedDto, AnnouncementUpsertRequest,
+};
+
+use crate::shared::api_client::{self, ApiError};
+
+/// Текст ошибки для пользователя: 400 отдаёт причину в теле.
+fn user_error(err: ApiError) -> String {
+    match err {
+        ApiError::Status {
+            status: 400,
+            message,
+            ..
+        } if !message.is_empty() => message,
+        err => err.to_string(),
+    }
+}
+
+pub async fn fetch_feed() -> Result<AnnouncementFeedDto, String> {
+    api_client::get_json("/api/system/announcements")
+        .await
+        .map_err(user_error)
+}
+
+pub async fn mark_all_read() -> Result<(), String> {
+    api_client::post_empty("/api/system/announcements/read")
+        .await
+        .map_err(user_error)
+}
+
+pub async fn fetch_all() -> Result<Vec<AnnouncementDto>, String> {
+    api_client::get_json("/api/sys/announcements")
+        .await
+        .map_err(user_error)
+}
+
+pub async fn create(request: &AnnouncementUpsertRequest) -> Result<AnnouncementDto, String> {
+    api_client::post_json("/api/sys/announcements", request)
+        .await
+        .map_err(user_error)
+}
+
+pub async fn update(id: &str, request: &AnnouncementUpsertRequest) -> Result<(), String> {
+    api_client::put_json(&format!("/api/sys/announcements/{}", id), request)
+        .await
+        .map_err(user_error)
+}
+
+pub async fn delete(id: &str) -> Result<(), String> {
+    api_client::delete(&format!("/api/sys/announcements/{}", id))
+        .await
+        .map_err(user_error)
+}
@@ -0,0 +1,459 @@
+use contracts::system::announcements::{
+    AnnouncementDto, AnnouncementFeedItem, AnnouncementUpsertRequest,
+};
+use gloo_timers::future::TimeoutFuture;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::{Checkbox, Input};
+
+use crate::layout::global_context::AppGlobalContext;
+use crate::shared::date_utils::format_datetime_utc_local;
+use crate::shared::icons::icon;
+use crate::shared::modal_frame::ModalFrame;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::announcements::api;
+use crate::system::auth::context::is_admin;
+use crate::system::auth::guard::RequireAdmin;
+
+/// Ключ вкладки управления объявлениями.
+pub const ADMIN_TAB_KEY: &str = "sys_announcements";
+
+/// Период опроса счётчика непрочитанных объявлений.
+const POLL_INTERVAL_MS: u32 = 300_000;
+
+fn date_label(raw: &str) -> String {
+    format_datetime_utc_local(raw, "%d.%m.%Y")
+}
+
+/// Кнопка «Что нового» в шапке: счётчик непрочитанных объявлений и панель со списком.
+#[component]
+pub fn AnnouncementsHeaderButton() -> impl IntoView {
+    let unread = RwSignal::new(0i64);
+    let modal_open = RwSignal::new(false);
+    let modal_closing = RwSignal::new(false);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            loop {
+                if let Ok(feed) = api::fetch_feed().await {
+                    unread.set(feed.unread);
+                }
+                TimeoutFuture::new(POLL_INTERVAL_MS).await;
+            }
+        });
+    });
+
+    let close_modal = Callback::new(move |_| {
+        if modal_closing.get_untracked() {
+            return;
+        }
+        modal_closing.set(true);
+        spawn_local(async move {
+            TimeoutFuture::new(180).await;
+            modal_open.set(false);
+            modal_closing.set(false);
+        });
+    });
+
+    view! {
+        <button
+            class="app-header__icon-button app-header__icon-button--badged"
+            on:click=move |_| {
+                modal_closing.set(false);
+                modal_open.set(true);
+            }
+            title=move || match unread.get() {
+                0 => "Что нового".to_string(),
+                n => format!("Что нового: {} непрочитанных", n),
+            }
+        >
+            {icon("megaphone")}
+            <Show when=move || { unread.get() > 0 }>
+                <span class="app-header__badge">
+                    {move || if unread.get() > 99 { "99+".to_string() } else { unread.get().to_string() }}
+                </span>
+            </Show>
+        </button>
+        <Show when=move || modal_open.get()>
+            <AnnouncementsDrawer on_close=close_modal closing=modal_closing unread=unread />
+        </Show>
+    }
+}
+
+/// Панель объявлений. При открытии все объявления отмечаются прочитанными,
+/// но новые до закрытия панели остаются подсвеченными.
+#[component]
+fn AnnouncementsDrawer(
+    on_close: Callback<()>,
+    closing: RwSignal<bool>,
+    unread: RwSignal<i64>,
+) -> impl IntoView {
+    let items = RwSignal::new(Vec::<AnnouncementFeedItem>::new());
+    let loading = RwSignal::new(true);
+    let error = RwSignal::new(None::<String>);
+    let tabs = use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
+    let can_manage = is_admin();
+
+    let open_admin = move |_| {
+        tabs.open_tab(ADMIN_TAB_KEY, "Что нового");
+        on_close.run(());
+    };
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match api::fetch_feed().await {
+                Ok(feed) => {
+                    let has_unread = feed.unread > 0;
+                    items.set(feed.items);
+                    if has_unread {
+                        match api::mark_all_read().await {
+                            Ok(()) => unread.set(0),
+                            Err(err) => error.set(Some(err)),
+                        }
+                    }
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    view! {
+        <ModalFrame
+            on_close=on_close
+            overlay_style="align-items: stretch; justify-content: flex-end; padding: 0;".to_string()
+            overlay_class_signal=Signal::derive(move || {
+                if closing.get() {
+                    "favorite-drawer-overlay favorite-drawer-overlay--closing".to_string()
+                } else {
+                    "favorite-drawer-overlay".to_string()
+                }
+            })
+            modal_style="width: min(520px, 100vw); max-width: min(520px, 100vw); height: 100vh; max-height: 100vh; border-radius: 0; overflow: hidden;".to_string()
+            modal_class_signal=Signal::derive(move || {
+                if closing.get() {
+                    "favorite-modal favorite-modal--list favorite-drawer favorite-drawer--closing".to_string()
+                } else {
+                    "favorite-modal favorite-modal--list favorite-drawer".to_string()
+                }
+            })
+        >
+            <div class="favorite-modal__header">
+                <h3>"Что нового"</h3>
+                <div style="display: flex; align-items: center; gap: 8px;">
+                    <Show when=move || can_manage>
+                        <button class="button button--secondary" on:click=open_admin title="Управление объявлениями">
+                            {icon("settings")}
+                        </button>
+                    </Show>
+                    <button class="favorite-modal__close" on:click=move |_| on_close.run(())>"x"</button>
+                </div>
+            </div>
+            <div class="favorite-modal__body">
+                <Show when=move || loading.get()>
+                    <div class="favorite-modal__loading">"Загрузка..."</div>
+                </Show>
+                <Show when=move || error.get().is_some()>
+                    <div class="favorite-modal__error">{move || error.get().unwrap_or_default()}</div>
+                </Show>
+                <Show when=move || !loading.get() && items.get().is_empty()>
+                    <div class="favorite-modal__empty">"Объявлений пока нет"</div>
+                </Show>
+                <div class="announcements__list">
+                    {move || {
+                        items
+                            .get()
+                            .into_iter()
+                            .map(|item| {
+                                let tab_key = item.tab_key.clone();
+                                let title = item.title.clone();
+                                let class = if item.read {
+                                    "announcements__item"
+                                } else {
+                                    "announcements__item announcements__item--new"
+                                };
+                                view! {
+                                    <article class=class>
+                                        <div class="announcements__item-head">
+                                            <span class="announcements__item-title">{item.title.clone()}</span>
+                                            {(!item.read).then(|| view! {
+                                                <span class="announcements__new-badge">"Новое"</span>
+                                            })}
+                                        </div>
+                                        <div class="announcements__item-meta">
+                                            {date_label(&item.published_at)}
+                                            {item.version.clone().map(|v| format!(" · версия {}", v))}
+                                        </div>
+                                        <div class="announcements__item-body">{item.body.clone()}</div>
+                                        {tab_key.map(|key| view! {
+                                            <button
+                                                class="button button--secondary announcements__open"
+                                                on:click=move |_| {
+                                                    tabs.open_tab(&key, &title);
+                                                    on_close.run(());
+                                                }
+                                            >
+                                                {icon("chevron-right")} "Открыть"
+                                            </button>
+                                        })}
+                                    </article>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </div>
+            </div>
+        </ModalFrame>
+    }
+}
+
+/// Управление объявлениями «Что нового» (только админ).
+#[component]
+pub fn AnnouncementsPage() -> impl IntoView {
+    view! {
+        <RequireAdmin>
+            <AnnouncementsContent />
+        </RequireAdmin>
+    }
+}
+
+#[component]
+fn AnnouncementsContent() -> impl IntoView {
+    let items = RwSignal::<Vec<AnnouncementDto>>::new(Vec::new());
+    let loading = RwSignal::new(false);
+    let saving = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let notice = RwSignal::<Option<String>>::new(None);
+
+    // Форма: `Some(None)` — новое объявление, `Some(Some(id))` — редактирование
+    let editing = RwSignal::<Option<Option<String>>>::new(None);
+    let title = RwSignal::new(String::new());
+    let body = RwSignal::new(String::new());
+    let version = RwSignal::new(String::new());
+    let tab_key = RwSignal::new(String::new());
+    let published = RwSignal::new(false);
+
+    let reload = Callback::new(move |_| {
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_all().await {
+                Ok(next) => items.set(next),
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+    });
+
+    let start_edit = move |item: Option<AnnouncementDto>| {
+        title.set(item.as_ref().map(|i| i.title.clone()).unwrap_or_default());
+        body.set(item.as_ref().map(|i| i.body.clone()).unwrap_or_default());
+        version.set(
+            item.as_ref()
+                .and_then(|i| i.version.clone())
+                .unwrap_or_default(),
+        );
+        tab_key.set(
+            item.as_ref()
+                .and_then(|i| i.tab_key.clone())
+                .unwrap_or_default(),
+        );
+        published.set(item.as_ref().is_some_and(|i| i.published_at.is_some()));
+        notice.set(None);
+        editing.set(Some(item.map(|i| i.id)));
+    };
+
+    let after_change = move |message: String| {
+        notice.set(Some(message));
+        editing.set(None);
+        reload.run(());
+    };
+
+    let save = move |_| {
+        let Some(target) = editing.get_untracked() else {
+            return;
+        };
+        let req = AnnouncementUpsertRequest {
+            title: title.get_untracked(),
+            body: body.get_untracked(),
+            version: Some(version.get_untracked()),
+            tab_key: Some(tab_key.get_untracked()),
+            published: published.get_untracked(),
+        };
+        saving.set(true);
+        error.set(None);
+        spawn_local(async move {
+            let result = match target.as_deref() {
+                Some(id) => api::update(id, &req).await,
+                None => api::create(&req).await.map(|_| ()),
+            };
+            match result {
+                Ok(()) => after_change(format!("Объявление «{}» сохранено", req.title.trim())),
+                Err(err) => error.set(Some(err)),
+            }
+            saving.set(false);
+        });
+    };
+
+    let remove = move |item: AnnouncementDto| {
+        let msg = format!("Удалить объявление «{}»?", item.title);
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message(&msg).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        saving.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::delete(&item.id).await {
+                Ok(()) => after_change(format!("Объявление «{}» удалено", item.title)),
+                Err(err) => error.set(Some(err)),
+            }
+            saving.set(false);
+        });
+    };
+
+    view! {
+        <PageFrame page_id="sys_announcements--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Что нового"</h1>
+                    <p class="page__subtitle">"Объявления о релизах и новых инструментах. Опубликованные появляются у всех пользователей под кнопкой в шапке; непрочитанные отмечаются счётчиком."</p>
+                </div>
+                <div class="page__header-right">
+                    <button
+                        class="button button--secondary"
+                        disabled=move || loading.get()
+                        on:click=move |_| reload.run(())
+                    >
+                        {icon("refresh-cw")}
+                        {move || if loading.get() { "Обновление данных..." } else { "Обновить данные" }}
+                    </button>
+                    <button class="button button--primary" on:click=move |_| start_edit(None)>
+                        {icon("plus")} "Новое объявление"
+                    </button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+                {move || notice.get().map(|msg| view! {
+                    <div class="alert alert--success">{msg}</div>
+                })}
+
+                {move || editing.get().map(|target| view! {
+                    <section class="raw-storage__section">
+                        <h2 class="raw-storage__section-title">
+                            {if target.is_some() { "Редактирование объявления" } else { "Новое объявление" }}
+                        </h2>
+                        <div class="raw-storage__list">
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label">"Заголовок"</span>
+                                <Input value=title placeholder="Массовое проведение WB продаж" />
+                            </div>
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label">"Текст"</span>
+                                <textarea
+                                    class="form__textarea"
+                                    rows="6"
+                                    prop:value=body
+                                    on:input=move |ev| body.set(event_target_value(&ev))
+                                />
+                            </div>
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label">"Версия релиза"</span>
+                                <Input value=version placeholder="2026.10" />
+                            </div>
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label">"Вкладка для перехода (ключ)"</span>
+                                <Input value=tab_key placeholder="sys_bulk_operations" />
+                            </div>
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label">"Публикация"</span>
+                                <Checkbox checked=published label="Опубликовать" />
+                            </div>
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label"></span>
+                                <div class="raw-storage__list-action-group">
+                                    <button class="button button--primary" disabled=move || saving.get() on:click=save>
+                                        {icon("check")} "Сохранить"
+                                    </button>
+                                    <button class="button button--secondary" on:click=move |_| editing.set(None)>
+                                        "Отмена"
+                                    </button>
+                                </div>
+                            </div>
+                        </div>
+                    </section>
+                })}
+
+                <section class="raw-storage__section">
+                    <div class="table-wrapper">
+                        <table class="table__data table--striped">
+                            <thead class="table__head">
+                                <tr>
+                                    <th class="table__header-cell">"Заголовок"</th>
+                                    <th class="table__header-cell">"Версия"</th>
+                                    <th class="table__header-cell">"Опубликовано"</th>
+                                    <th class="table__header-cell">"Изменено"</th>
+                                    <th class="table__header-cell"></th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {move || {
+                                    items
+                                        .get()
+                                        .into_iter()
+                                        .map(|item| {
+                                            let for_edit = item.clone();
+                                            let for_delete = item.clone();
+                                            view! {
+                                                <tr class="table__row">
+                                                    <td class="table__cell">{item.title.clone()}</td>
+                                                    <td class="table__cell">{item.version.clone().unwrap_or_default()}</td>
+                                                    <td class="table__cell">
+                                                        {item
+                                                            .published_at
+                                                            .as_deref()
+                                                            .map(date_label)
+                                                            .unwrap_or_else(|| "Черновик".to_string())}
+                                                    </td>
+                                                    <td class="table__cell">{date_label(&item.updated_at)}</td>
+                                                    <td class="table__cell">
+                                                        <div class="raw-storage__list-action-group">
+                                                            <button
+                                                                class="button button--secondary"
+                                                                on:click=move |_| start_edit(Some(for_edit.clone()))
+                                                            >
+                                                                {icon("edit")} "Изменить"
+                                                            </button>
+                                                            <button
+                                                                class="button button--secondary"
+                                                                disabled=move || saving.get()
+                                                                on:click=move |_| remove(for_delete.clone())
+                                                            >
+                                                                {icon("trash-2")}
+                                                            </button>
+                                                        </div>
+                                                    </td>
+                                                </tr>
+                                            }
+                                        })
+                                        .collect_view()
+                                }}
+                            </tbody>
+                        </table>
+                    </div>
+                </section>
+            </div>
+        </PageFrame>
+    }
+}
@@ -1,5 +1,6 @@
 use contracts::system::auth::{
-    LoginRequest, LoginResponse, RefreshRequest, RefreshResponse, UserInfo,
+    LoginRequest, LoginResponse, OidcExchangeRequest, OidcLoginInfo, RefreshRequest,
+    RefreshResponse, UserInfo,
 };
 use gloo_net::http::Request;
 
@@ -26,6 +27,49 @@ pub async fn login(username: String, password: String) -> Result<LoginResponse,
         .map_err(|e| format!("Failed to parse response: {}", e))
 }
 
+/// Доступен ли вход через корпоративный OIDC-провайдер
+pub async fn fetch_oidc_info() -> Result<OidcLoginInfo, String> {
+    let response = Request::get(&format!("{}/api/system/auth/oidc", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!("OIDC info failed: {}", response.status()));
+    }
+
+    response
+        .json::<OidcLoginInfo>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+/// Адрес, с которого браузер уходит к OIDC-провайдеру
+pub fn oidc_start_url() -> String {
+    format!("{}/api/system/auth/oidc/start", api_base())
+}
+
+/// Обмен одноразового кода после входа через OIDC на токены
+pub async fn exchange_oidc_code(code: String) -> Result<LoginResponse, String> {
+    let request = OidcExchangeRequest { code };
+
+    let response = Request::post(&format!("{}/api/system/auth/oidc/exchange", api_base()))
+        .json(&request)
+        .map_err(|e| format!("Failed to serialize request: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!("Login failed: {}", response.status()));
+    }
+
+    response
+        .json::<LoginResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
 /// Refresh access token using refresh token
 pub async fn refresh_token(refresh_token: String) -> Result<RefreshResponse, String> {
     let request = RefreshRequest { refresh_token };
@@ -1,4 +1,6 @@
+use contracts::shared::access::VIEW_FINANCE_SCOPE;
 use contracts::system::auth::UserInfo;
+use contracts::system::roles::RolePreview;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 
@@ -8,6 +10,27 @@ use super::{api, storage};
 pub struct AuthState {
     pub access_token: Option<String>,
     pub user_info: Option<UserInfo>,
+    /// Admin "view as role" preview: while set, menu, card policies and access
+    /// helpers use the previewed role instead of the admin's own rights.
+    /// Frontend-only and in-memory — API calls still run with the admin's token.
+    pub preview: Option<RolePreview>,
+}
+
+impl AuthState {
+    /// User as the UI should treat it: the real user, or — during a role
+    /// preview — the same user with the previewed role and scopes and no admin bypass.
+    pub fn effective_user(&self) -> Option<UserInfo> {
+        let user = self.user_info.clone()?;
+        match &self.preview {
+            None => Some(user),
+            Some(preview) => Some(UserInfo {
+                is_admin: false,
+                primary_role: preview.primary_role.clone(),
+                scopes: preview.scopes.clone(),
+                ..user
+            }),
+        }
+    }
 }
 
 /// Auth context provider component
@@ -25,6 +48,7 @@ pub fn AuthProvider(children: ChildrenFn) -> impl IntoView {
                         set_auth_state.set(AuthState {
                             access_token: Some(access_token),
                             user_info: Some(user_info),
+                            preview: None,
                         });
                     }
                     Err(_) => {
@@ -41,6 +65,7 @@ pub fn AuthProvider(children: ChildrenFn) -> impl IntoView {
                                         set_auth_state.set(AuthState {
                                             access_token: Some(response.access_token),
                                             user_info: Some(user_info),
+                                            preview: None,
                                         });
                                     }
                                 }
@@ -138,11 +163,11 @@ pub fn is_admin() -> bool {
 }
 
 /// Helper: Check if the current user has at least read access to a scope.
-/// Admin users always return true.
+/// Admin users always return true (unless a role preview is active).
 /// Call only inside a reactive context (uses `use_auth` hook).
 pub fn has_read_access(auth_state: ReadSignal<AuthState>, scope_id: &str) -> bool {
     auth_state.with_untracked(|s| {
-        let Some(user) = &s.user_info else {
+        let Some(user) = s.effective_user() else {
             return false;
         };
         if user.is_admin {
@@ -154,6 +179,38 @@ pub fn has_read_access(auth_state: ReadSignal<AuthState>, scope_id: &str) -> boo
     })
 }
 
+/// Helper: Check if the current user has write access ("all") to a scope.
+/// Admin users always return true (unless a role preview is active).
+pub fn has_write_access(auth_state: ReadSignal<AuthState>, scope_id: &str) -> bool {
+    auth_state.with_untracked(|s| {
+        let Some(user) = s.effective_user() else {
+            return false;
+        };
+        if user.is_admin {
+            return true;
+        }
+        user.scopes
+            .iter()
+            .any(|s| s.scope_id == scope_id && s.mode == "all")
+    })
+}
+
+/// Helper: Check if the current user may see finance fields (суммы, цены, маржа).
+/// Admin users always return true.
+pub fn can_view_finance(auth_state: ReadSignal<AuthState>) -> bool {
+    has_read_access(auth_state, VIEW_FINANCE_SCOPE)
+}
+
+/// Helper: Start the "view as role" preview (admin only, see `AuthState::preview`).
+pub fn start_role_preview(set_auth_state: WriteSignal<AuthState>, preview: RolePreview) {
+    set_auth_state.update(|s| s.preview = Some(preview));
+}
+
+/// Helper: Return to the admin's own rights.
+pub fn stop_role_preview(set_auth_state: WriteSignal<AuthState>) {
+    set_auth_state.update(|s| s.preview = None);
+}
+
 /// Helper: Perform login
 pub async fn do_login(username: String, password: String) -> Result<(), String> {
     let response = api::login(username, password).await?;
@@ -167,6 +224,7 @@ pub async fn do_login(username: String, password: String) -> Result<(), String>
     set_auth_state.set(AuthState {
         access_token: Some(response.access_token),
         user_info: Some(response.user),
+        preview: None,
     });
 
     Ok(())
@@ -0,0 +1,98 @@
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+use contracts::system::branding::{BrandingDto, OrgBrandingDto, OrgBrandingUpsertRequest};
+use gloo_net::http::Request;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+/// Брендирование для текущего хоста (без авторизации — нужно и странице входа).
+pub async fn fetch_current() -> Result<BrandingDto, String> {
+    let response = Request::get(&format!("{}/api/system/branding", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch branding: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch branding: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse branding: {}", e))
+}
+
+pub async fn fetch_all() -> Result<Vec<OrgBrandingDto>, String> {
+    let response = Request::get(&format!("{}/api/sys/branding", api_base()))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch branding list: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch branding list: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse branding list: {}", e))
+}
+
+pub async fn save(req: OrgBrandingUpsertRequest) -> Result<OrgBrandingDto, String> {
+    let response = Request::put(&format!("{}/api/sys/branding", api_base()))
+        .header("Authorization", &auth_header()?)
+        .json(&req)
+        .map_err(|e| format!("Failed to serialize branding: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to save branding: {}", e))?;
+
+    if response.status() == 400 {
+        return Err(
+            "Проверьте поля: название до 60 символов, цвет #rrggbb, логотип — URL или data:image до 256 КБ, хосты не заняты другой организацией"
+                .to_string(),
+        );
+    }
+    if !response.ok() {
+        return Err(format!(
+            "Failed to save branding: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse saved branding: {}", e))
+}
+
+pub async fn delete(organization_id: &str) -> Result<(), String> {
+    let response = Request::delete(&format!(
+        "{}/api/sys/branding/{}",
+        api_base(),
+        urlencoding::encode(organization_id)
+    ))
+    .header("Authorization", &auth_header()?)
+    .send()
+    .await
+    .map_err(|e| format!("Failed to delete branding: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to delete branding: HTTP {}",
+            response.status()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,95 @@
+//! Брендирование текущего хоста: загружается при старте приложения и применяется
+//! поверх выбранной темы — акцентный цвет заменяет `--color-primary*` на `<html>`,
+//! название продукта попадает в заголовок окна, шапку и страницу входа.
+
+use contracts::system::branding::BrandingDto;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use wasm_bindgen::JsCast;
+
+use super::api;
+
+#[derive(Clone, Copy)]
+pub struct BrandingContext(pub RwSignal<BrandingDto>);
+
+/// Переменные темы, которые перекрывает акцентный цвет, и прозрачность для каждой
+/// (`None` — сам цвет).
+const PRIMARY_VARS: &[(&str, Option<f64>)] = &[
+    ("--color-primary", None),
+    ("--color-primary-50", Some(0.10)),
+    ("--color-primary-100", Some(0.20)),
+    ("--color-primary-200", Some(0.35)),
+    ("--color-primary-800", Some(0.35)),
+    ("--color-primary-900", Some(0.20)),
+];
+
+fn rgba(hex: &str, alpha: f64) -> Option<String> {
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some(format!(
+        "rgba({}, {}, {}, {:.2})",
+        channel(1)?,
+        channel(3)?,
+        channel(5)?,
+        alpha
+    ))
+}
+
+/// Применяет брендирование к документу. Без акцентного цвета возвращает цвета темы.
+pub fn apply_branding(branding: &BrandingDto) {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return;
+    };
+    document.set_title(&branding.product_name);
+
+    let Some(root) = document
+        .document_element()
+        .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+    else {
+        return;
+    };
+    let style = root.style();
+    for (name, alpha) in PRIMARY_VARS {
+        let value = branding
+            .accent_color
+            .as_deref()
+            .and_then(|hex| match alpha {
+                None => Some(hex.to_string()),
+                Some(a) => rgba(hex, *a),
+            });
+        match value {
+            Some(value) => {
+                let _ = style.set_property(name, &value);
+            }
+            None => {
+                let _ = style.remove_property(name);
+            }
+        }
+    }
+}
+
+/// Перезапрашивает брендирование (например, после правки на странице настроек).
+pub fn reload_branding(ctx: BrandingContext) {
+    spawn_local(async move {
+        match api::fetch_current().await {
+            Ok(branding) => {
+                apply_branding(&branding);
+                ctx.0.set(branding);
+            }
+            Err(err) => leptos::logging::warn!("Branding not loaded: {}", err),
+        }
+    });
+}
+
+/// Создаёт контекст брендирования и загружает настройки для текущего хоста.
+pub fn provide_branding() {
+    let ctx = BrandingContext(RwSignal::new(BrandingDto::default()));
+    provide_context(ctx);
+    reload_branding(ctx);
+}
+
+/// Текущее брендирование; вне `App` — стандартный вид.
+pub fn use_branding() -> RwSignal<BrandingDto> {
+    use_context::<BrandingContext>()
+        .map(|ctx| ctx.0)
+        .unwrap_or_else(|| RwSignal::new(BrandingDto::default()))
+}
@@ -0,0 +1,321 @@
+use contracts::system::branding::{
+    OrgBrandingDto, OrgBrandingSettings, OrgBrandingUpsertRequest, DEFAULT_PRODUCT_NAME,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::{Checkbox, Input};
+
+use crate::shared::icons::icon;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::auth::guard::RequireAdmin;
+use crate::system::branding::api;
+use crate::system::branding::context::{reload_branding, BrandingContext};
+
+const DEFAULT_ACCENT: &str = "#2563eb";
+
+#[component]
+pub fn BrandingPage() -> impl IntoView {
+    view! {
+        <RequireAdmin>
+            <BrandingContent />
+        </RequireAdmin>
+    }
+}
+
+#[component]
+fn BrandingContent() -> impl IntoView {
+    let items = RwSignal::<Vec<OrgBrandingDto>>::new(Vec::new());
+    let loading = RwSignal::new(false);
+    let saving = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let notice = RwSignal::<Option<String>>::new(None);
+
+    // Форма редактирования выбранной организации
+    let editing = RwSignal::<Option<OrgBrandingDto>>::new(None);
+    let product_name = RwSignal::new(String::new());
+    let use_accent = RwSignal::new(false);
+    let accent_color = RwSignal::new(DEFAULT_ACCENT.to_string());
+    let logo_url = RwSignal::new(String::new());
+    let hosts = RwSignal::new(String::new());
+    let is_default = RwSignal::new(false);
+
+    let branding_ctx = use_context::<BrandingContext>();
+
+    let reload = Callback::new(move |_| {
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_all().await {
+                Ok(next) => items.set(next),
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+    });
+
+    let start_edit = move |item: OrgBrandingDto| {
+        let settings = item.settings.clone();
+        product_name.set(
+            settings
+                .as_ref()
+                .map(|s| s.product_name.clone())
+                .unwrap_or_else(|| DEFAULT_PRODUCT_NAME.to_string()),
+        );
+        let accent = settings.as_ref().and_then(|s| s.accent_color.clone());
+        use_accent.set(accent.is_some());
+        accent_color.set(accent.unwrap_or_else(|| DEFAULT_ACCENT.to_string()));
+        logo_url.set(
+            settings
+                .as_ref()
+                .and_then(|s| s.logo_url.clone())
+                .unwrap_or_default(),
+        );
+        hosts.set(
+            settings
+                .as_ref()
+                .map(|s| s.hosts.join(", "))
+                .unwrap_or_default(),
+        );
+        is_default.set(settings.map(|s| s.is_default).unwrap_or(false));
+        notice.set(None);
+        editing.set(Some(item));
+    };
+
+    let after_change = move |message: String| {
+        notice.set(Some(message));
+        editing.set(None);
+        reload.run(());
+        if let Some(ctx) = branding_ctx {
+            reload_branding(ctx);
+        }
+    };
+
+    let save = move |_| {
+        let Some(item) = editing.get_untracked() else {
+            return;
+        };
+        let req = OrgBrandingUpsertRequest {
+            organization_id: item.organization_id.clone(),
+            settings: OrgBrandingSettings {
+                product_name: product_name.get_untracked(),
+                accent_color: use_accent
+                    .get_untracked()
+                    .then(|| accent_color.get_untracked()),
+                logo_url: Some(logo_url.get_untracked()),
+                hosts: hosts
+                    .get_untracked()
+                    .split(',')
+                    .map(|h| h.trim().to_string())
+                    .filter(|h| !h.is_empty())
+                    .collect(),
+                is_default: is_default.get_untracked(),
+            },
+        };
+        saving.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::save(req).await {
+                Ok(_) => after_change(format!(
+                    "Брендирование «{}» сохранено",
+                    item.organization_name
+                )),
+                Err(err) => error.set(Some(err)),
+            }
+            saving.set(false);
+        });
+    };
+
+    let reset = move |_| {
+        let Some(item) = editing.get_untracked() else {
+            return;
+        };
+        let msg = format!(
+            "Удалить брендирование «{}»? Её хосты получат стандартный вид.",
+            item.organization_name
+        );
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message(&msg).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        saving.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::delete(&item.organization_id).await {
+                Ok(()) => after_change(format!(
+                    "Брендирование «{}» удалено",
+                    item.organization_name
+                )),
+                Err(err) => error.set(Some(err)),
+            }
+            saving.set(false);
+        });
+    };
+
+    view! {
+        <PageFrame page_id="sys_branding--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Брендирование"</h1>
+                    <p class="page__subtitle">"Название продукта, акцентный цвет и логотип для организаций-клиентов. Вид выбирается по хосту, с которого открыт сервис; для остальных хостов — по организации «по умолчанию»."</p>
+                </div>
+                <div class="page__header-right">
+                    <button
+                        class="button button--secondary"
+                        disabled=move || loading.get()
+                        on:click=move |_| reload.run(())
+                    >
+                        {icon("refresh-cw")}
+                        {move || if loading.get() { "Обновление данных..." } else { "Обновить данные" }}
+                    </button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+                {move || notice.get().map(|msg| view! {
+                    <div class="alert alert--success">{msg}</div>
+                })}
+
+                {move || editing.get().map(|item| view! {
+                    <section class="raw-storage__section">
+                        <h2 class="raw-storage__section-title">{item.organization_name.clone()}</h2>
+                        <div class="raw-storage__list">
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label">"Название продукта"</span>
+                                <Input value=product_name placeholder=DEFAULT_PRODUCT_NAME />
+                            </div>
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label">"Акцентный цвет"</span>
+                                <div class="raw-storage__list-action-group">
+                                    <Checkbox checked=use_accent label="Свой цвет" />
+                                    <input
+                                        type="color"
+                                        prop:value=move || accent_color.get()
+                                        disabled=move || !use_accent.get()
+                                        on:input=move |ev| accent_color.set(event_target_value(&ev))
+                                    />
+                                    <span class="raw-storage__list-value">{move || accent_color.get()}</span>
+                                </div>
+                            </div>
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label">"Логотип (URL или data:image)"</span>
+                                <div class="raw-storage__list-action-group">
+                                    <Input value=logo_url placeholder="https://…/logo.svg" />
+                                    {move || {
+                                        let url = logo_url.get();
+                                        (!url.trim().is_empty()).then(|| view! {
+                                            <img src=url alt="logo" style="height: 32px; max-width: 120px; object-fit: contain;" />
+                                        })
+                                    }}
+                                </div>
+                            </div>
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label">"Хосты (через запятую)"</span>
+                                <Input value=hosts placeholder="client.example.com" />
+                            </div>
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label">"Для остальных хостов"</span>
+                                <Checkbox checked=is_default label="По умолчанию" />
+                            </div>
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label"></span>
+                                <div class="raw-storage__list-action-group">
+                                    <button class="button button--primary" disabled=move || saving.get() on:click=save>
+                                        {icon("check")} "Сохранить"
+                                    </button>
+                                    <Show when={
+                                        let configured = item.settings.is_some();
+                                        move || configured
+                                    }>
+                                        <button class="button button--secondary" disabled=move || saving.get() on:click=reset>
+                                            {icon("x")} "Удалить брендирование"
+                                        </button>
+                                    </Show>
+                                    <button class="button button--secondary" on:click=move |_| editing.set(None)>
+                                        "Отмена"
+                                    </button>
+                                </div>
+                            </div>
+                        </div>
+                    </section>
+                })}
+
+                <section class="raw-storage__section">
+                    <div class="table-wrapper">
+                        <table class="table__data table--striped">
+                            <thead class="table__head">
+                                <tr>
+                                    <th class="table__header-cell">"Организация"</th>
+                                    <th class="table__header-cell">"Название продукта"</th>
+                                    <th class="table__header-cell">"Цвет"</th>
+                                    <th class="table__header-cell">"Хосты"</th>
+                                    <th class="table__header-cell">"Изменено"</th>
+                                    <th class="table__header-cell"></th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {move || {
+                                    items
+                                        .get()
+                                        .into_iter()
+                                        .map(|item| {
+                                            let settings = item.settings.clone();
+                                            let color = settings.as_ref().and_then(|s| s.accent_color.clone());
+                                            let for_edit = item.clone();
+                                            view! {
+                                                <tr class="table__row">
+                                                    <td class="table__cell">{item.organization_name.clone()}</td>
+                                                    <td class="table__cell">
+                                                        {settings
+                                                            .as_ref()
+                                                            .map(|s| {
+                                                                if s.is_default {
+                                                                    format!("{} (по умолчанию)", s.product_name)
+                                                                } else {
+                                                                    s.product_name.clone()
+                                                                }
+                                                            })
+                                                            .unwrap_or_else(|| "—".to_string())}
+                                                    </td>
+                                                    <td class="table__cell">
+                                                        {color.map(|c| view! {
+                                                            <span
+                                                                title=c.clone()
+                                                                style=format!("display: inline-block; width: 16px; height: 16px; border-radius: 4px; border: 1px solid var(--color-border); background: {};", c)
+                                                            ></span>
+                                                        })}
+                                                    </td>
+                                                    <td class="table__cell">
+                                                        {settings.as_ref().map(|s| s.hosts.join(", ")).unwrap_or_default()}
+                                                    </td>
+                                                    <td class="table__cell">{item.updated_at.clone().unwrap_or_default()}</td>
+                                                    <td class="table__cell">
+                                                        <button
+                                                            class="button button--secondary"
+                                                            on:click=move |_| start_edit(for_edit.clone())
+                                                        >
+                                                            {icon("edit")} "Настроить"
+                                                        </button>
+                                                    </td>
+                                                </tr>
+                                            }
+                                        })
+                                        .collect_view()
+                                }}
+                            </tbody>
+                        </table>
+                    </div>
+                </section>
+            </div>
+        </PageFrame>
+    }
+}
@@ -0,0 +1,63 @@
+use contracts::system::bulk_import::BulkImportResultDto;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Request, RequestInit, RequestMode, Response};
+
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+/// Отправить NDJSON-файл (можно `.gz`) телом запроса — сервер читает его потоком.
+pub async fn import_p903(
+    connection_id: &str,
+    rebuild: bool,
+    file: web_sys::File,
+) -> Result<BulkImportResultDto, String> {
+    let opts = RequestInit::new();
+    opts.set_method("POST");
+    opts.set_mode(RequestMode::Cors);
+    opts.set_body(&file);
+
+    let url = format!(
+        "{}/api/sys/bulk-import/p903?connection_id={}&rebuild={}",
+        api_base(),
+        urlencoding::encode(connection_id),
+        rebuild
+    );
+    let request = Request::new_with_str_and_init(&url, &opts).map_err(|e| format!("{e:?}"))?;
+    let headers = request.headers();
+    headers
+        .set("Authorization", &auth_header()?)
+        .map_err(|e| format!("{e:?}"))?;
+    headers
+        .set("Content-Type", "application/x-ndjson")
+        .map_err(|e| format!("{e:?}"))?;
+
+    let window = web_sys::window().ok_or_else(|| "No window object".to_string())?;
+    let response_value = JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(|e| format!("Upload request failed: {e:?}"))?;
+    let response: Response = response_value
+        .dyn_into()
+        .map_err(|e| format!("Invalid upload response: {e:?}"))?;
+
+    match response.status() {
+        400 => return Err("Некорректный идентификатор подключения".to_string()),
+        404 => return Err("Подключение не найдено".to_string()),
+        _ if !response.ok() => return Err(format!("Upload failed: HTTP {}", response.status())),
+        _ => {}
+    }
+
+    let text = JsFuture::from(response.text().map_err(|e| format!("{e:?}"))?)
+        .await
+        .map_err(|e| format!("{e:?}"))?
+        .as_string()
+        .ok_or_else(|| "Upload response is not text".to_string())?;
+    serde_json::from_str::<BulkImportResultDto>(&text)
+        .map_err(|e| format!("Failed to parse import result: {}", e))
+}
@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::common::AggregateId;
+use contracts::enums::marketplace_type::MarketplaceType;
+use contracts::system::bulk_import::BulkImportResultDto;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use wasm_bindgen::JsCast;
+
+use crate::shared::date_utils::format_bytes_compact;
+use crate::shared::icons::icon;
+use crate::shared::money_format::format_number;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::auth::guard::RequireAdmin;
+use crate::system::bulk_import::api;
+use crate::usecases::u504_import_from_wildberries::api as u504_api;
+
+#[component]
+pub fn BulkImportPage() -> impl IntoView {
+    view! {
+        <RequireAdmin>
+            <BulkImportContent />
+        </RequireAdmin>
+    }
+}
+
+async fn load_wb_connections() -> Result<Vec<ConnectionMP>, String> {
+    let types: HashMap<String, Option<MarketplaceType>> = u504_api::get_marketplaces()
+        .await?
+        .into_iter()
+        .map(|mp| (mp.base.id.as_string(), mp.marketplace_type))
+        .collect();
+    Ok(u504_api::get_connections()
+        .await?
+        .into_iter()
+        .filter(|conn| {
+            matches!(
+                types.get(&conn.marketplace_id),
+                Some(Some(MarketplaceType::Wildberries))
+            )
+        })
+        .collect())
+}
+
+#[component]
+fn BulkImportContent() -> impl IntoView {
+    let connections = RwSignal::new(Vec::<ConnectionMP>::new());
+    let selected_connection = RwSignal::new(String::new());
+    let selected_file = StoredValue::new_local(None::<web_sys::File>);
+    let file_info = RwSignal::<Option<(String, u64)>>::new(None);
+    let rebuild = RwSignal::new(false);
+    let busy = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let result = RwSignal::<Option<BulkImportResultDto>>::new(None);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match load_wb_connections().await {
+                Ok(list) => {
+                    if let Some(first) = list.first() {
+                        selected_connection.set(first.to_string_id());
+                    }
+                    connections.set(list);
+                }
+                Err(err) => error.set(Some(format!("Ошибка загрузки подключений: {}", err))),
+            }
+        });
+    });
+
+    let run_import = move |_| {
+        let Some(file) = selected_file.get_value() else {
+            error.set(Some("Выберите файл".to_string()));
+            return;
+        };
+        let connection_id = selected_connection.get_untracked();
+        if connection_id.is_empty() {
+            error.set(Some("Выберите подключение WB".to_string()));
+            return;
+        }
+        busy.set(true);
+        error.set(None);
+        result.set(None);
+        let rebuild = rebuild.get_untracked();
+        spawn_local(async move {
+            match api::import_p903(&connection_id, rebuild, file).await {
+                Ok(outcome) => result.set(Some(outcome)),
+                Err(err) => error.set(Some(err)),
+            }
+            busy.set(false);
+        });
+    };
+
+    view! {
+        <PageFrame page_id="sys_bulk_import--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Массовая загрузка"</h1>
+                    <p class="page__subtitle">"Загрузка истории финансового отчёта WB (p903) из NDJSON-файла: одна строка — один объект reportDetailByPeriod. Файл можно сжать gzip. Строки пишутся пачками по ключу rrd_id, повторная загрузка обновляет их."</p>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+
+                <section class="raw-storage__section">
+                    <div class="raw-storage__list">
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Подключение WB"</span>
+                            <select
+                                class="form__select"
+                                prop:value=move || selected_connection.get()
+                                on:change=move |ev| selected_connection.set(event_target_value(&ev))
+                            >
+                                {move || connections
+                                    .get()
+                                    .into_iter()
+                                    .map(|conn| view! {
+                                        <option value=conn.to_string_id()>{conn.base.description.clone()}</option>
+                                    })
+                                    .collect_view()}
+                            </select>
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Файл (.ndjson, .jsonl, .gz)"</span>
+                            <div class="raw-storage__list-action-group">
+                                <input
+                                    class="form__input form__input--file"
+                                    type="file"
+                                    accept=".ndjson,.jsonl,.json,.gz"
+                                    on:change=move |ev| {
+                                        let file = ev
+                                            .target()
+                                            .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+                                            .and_then(|input| input.files())
+                                            .and_then(|list| list.get(0));
+                                        file_info.set(file.as_ref().map(|f| (f.name(), f.size() as u64)));
+                                        selected_file.set_value(file);
+                                        result.set(None);
+                                    }
+                                />
+                                {move || file_info.get().map(|(name, size)| view! {
+                                    <span class="text-muted">{format!("{} ({})", name, format_bytes_compact(size))}</span>
+                                })}
+                            </div>
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Перепровести загруженный период (номенклатура, GL, p914)"</span>
+                            <input
+                                type="checkbox"
+                                prop:checked=move || rebuild.get()
+                                on:change=move |ev| rebuild.set(event_target_checked(&ev))
+                            />
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">
+                                "Без перепроведения строки попадут в p903 без ссылок на номенклатуру — перепроведите период позже через «Перепроведение документов»."
+                            </span>
+                            <button class="button button--primary" disabled=move || busy.get() on:click=run_import>
+                                {icon("upload")}
+                                {move || if busy.get() { "Загрузка..." } else { "Загрузить" }}
+                            </button>
+                        </div>
+                    </div>
+                </section>
+
+                {move || result.get().map(|r| view! { <BulkImportReport result=r /> })}
+            </div>
+        </PageFrame>
+    }
+}
+
+#[component]
+fn BulkImportReport(result: BulkImportResultDto) -> impl IntoView {
+    let period = match (&result.date_from, &result.date_to) {
+        (Some(from), Some(to)) => format!("{} — {}", from, to),
+        _ => "—".to_string(),
+    };
+    let alert_class = if result.rejected == 0 {
+        "alert alert--success"
+    } else {
+        "alert alert--error"
+    };
+    let summary = format!(
+        "Строк в файле: {}, записано: {}, отброшено: {}, пачек: {}",
+        format_number(result.total_lines as f64, 0),
+        format_number(result.upserted as f64, 0),
+        format_number(result.rejected as f64, 0),
+        result.chunks
+    );
+    let rebuilt = result
+        .rebuilt_days
+        .map(|days| format!("Перепроведено дней: {}", days));
+    let truncated = result.errors_truncated;
+    let errors = result.errors;
+
+    view! {
+        <section class="raw-storage__section">
+            <div class=alert_class>{summary}</div>
+            <div class="raw-storage__list">
+                <div class="raw-storage__list-row">
+                    <span class="raw-storage__list-label">"Период загруженных строк"</span>
+                    <span class="raw-storage__list-value">{period}</span>
+                </div>
+                {rebuilt.map(|text| view! {
+                    <div class="raw-storage__list-row">
+                        <span class="raw-storage__list-label">{text}</span>
+                    </div>
+                })}
+            </div>
+        </section>
+
+        {(!errors.is_empty()).then(|| view! {
+            <section class="raw-storage__section">
+                <h2 class="raw-storage__section-title">
+                    {if truncated { "Ошибки (показаны первые)" } else { "Ошибки" }}
+                </h2>
+                <div class="table-wrapper">
+                    <table class="table__data table--striped">
+                        <thead class="table__head">
+                            <tr>
+                                <th class="table__header-cell" style="text-align: right;">"Пачка"</th>
+                                <th class="table__header-cell" style="text-align: right;">"Строка"</th>
+                                <th class="table__header-cell">"Ошибка"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {errors
+                                .into_iter()
+                                .map(|e| view! {
+                                    <tr class="table__row">
+                                        <td class="table__cell" style="text-align: right;">{e.chunk + 1}</td>
+                                        <td class="table__cell" style="text-align: right;">{e.line}</td>
+                                        <td class="table__cell">{e.message}</td>
+                                    </tr>
+                                })
+                                .collect_view()}
+                        </tbody>
+                    </table>
+                </div>
+            </section>
+        })}
+    }
+}
@@ -0,0 +1,128 @@
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+use crate::system::operations::api::run_operation;
+use contracts::system::bulk_ops::{
+    BulkActionResultDto, BulkDocumentRef, BulkLookupRequest, BulkLookupResponse,
+    BulkOperationListResponse, BulkUndoResultDto, BulkUndoWindowDto,
+};
+use contracts::system::operations::OperationRequest;
+use gloo_net::http::Request;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+pub async fn fetch_operations() -> Result<BulkOperationListResponse, String> {
+    let response = Request::get(&format!("{}/api/sys/bulk-operations", api_base()))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch bulk operations: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch bulk operations: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse bulk operations: {}", e))
+}
+
+pub async fn undo_operation(id: &str) -> Result<BulkUndoResultDto, String> {
+    let response = Request::post(&format!(
+        "{}/api/sys/bulk-operations/{}/undo",
+        api_base(),
+        id
+    ))
+    .header("Authorization", &auth_header()?)
+    .send()
+    .await
+    .map_err(|e| format!("Failed to undo operation: {}", e))?;
+
+    if response.status() == 409 {
+        return Err("Окно отмены истекло или операция уже отменена".to_string());
+    }
+    if !response.ok() {
+        return Err(format!(
+            "Failed to undo operation: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse undo result: {}", e))
+}
+
+pub async fn update_undo_window(minutes: i64) -> Result<BulkUndoWindowDto, String> {
+    let dto = BulkUndoWindowDto { minutes };
+    let response = Request::post(&format!(
+        "{}/api/sys/bulk-operations/undo-window",
+        api_base()
+    ))
+    .header("Authorization", &auth_header()?)
+    .json(&dto)
+    .map_err(|e| format!("Failed to serialize undo window: {}", e))?
+    .send()
+    .await
+    .map_err(|e| format!("Failed to update undo window: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to update undo window: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse undo window: {}", e))
+}
+
+pub async fn lookup_documents(ids: Vec<String>) -> Result<BulkLookupResponse, String> {
+    let response = Request::post(&format!("{}/api/sys/bulk-operations/lookup", api_base()))
+        .header("Authorization", &auth_header()?)
+        .json(&BulkLookupRequest { ids })
+        .map_err(|e| format!("Failed to serialize ID list: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to look up documents: {}", e))?;
+
+    if response.status() == 413 {
+        return Err("Слишком длинный список номеров".to_string());
+    }
+    if !response.ok() {
+        return Err(format!(
+            "Failed to look up documents: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse lookup result: {}", e))
+}
+
+/// Проведение найденных документов пакетной операцией; ждёт её завершения.
+pub async fn post_documents(
+    documents: Vec<BulkDocumentRef>,
+) -> Result<BulkActionResultDto, String> {
+    run_operation(OperationRequest::PostByList { documents }, |_, _| {}).await
+}
+
+/// Метка на найденные документы пакетной операцией; ждёт её завершения.
+pub async fn tag_documents(
+    documents: Vec<BulkDocumentRef>,
+    tag: String,
+) -> Result<BulkActionResultDto, String> {
+    run_operation(OperationRequest::TagByList { documents, tag }, |_, _| {}).await
+}
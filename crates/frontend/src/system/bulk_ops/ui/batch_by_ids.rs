@@ -0,0 +1,259 @@
+use contracts::system::bulk_ops::{
+    parse_id_list, BulkActionResultDto, BulkDocumentRef, BulkLookupItemDto, BulkLookupResponse,
+    BULK_LOOKUP_MAX_IDS,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::layout::tabs::tab_labels::tab_label_for_key;
+use crate::shared::export::{export_to_excel, ExcelExportable};
+use crate::shared::icons::icon;
+use crate::shared::modal_frame::ModalFrame;
+use crate::system::bulk_ops::api;
+
+impl ExcelExportable for BulkLookupItemDto {
+    fn headers() -> Vec<&'static str> {
+        vec!["Номер", "Тип документа", "Документ", "Проведён", "Метки"]
+    }
+
+    fn to_csv_row(&self) -> Vec<String> {
+        vec![
+            self.document_no.clone(),
+            tab_label_for_key(&self.entity_type).to_string(),
+            self.description.clone(),
+            if self.is_posted { "Да" } else { "Нет" }.to_string(),
+            self.tags.join(", "),
+        ]
+    }
+}
+
+fn document_refs(result: &Option<BulkLookupResponse>) -> Vec<BulkDocumentRef> {
+    result
+        .as_ref()
+        .map(|r| {
+            r.items
+                .iter()
+                .map(|i| BulkDocumentRef {
+                    entity_type: i.entity_type.clone(),
+                    id: i.id.clone(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn action_message(verb: &str, result: &BulkActionResultDto) -> Result<String, String> {
+    if result.failed == 0 {
+        Ok(format!("{}: {}", verb, result.succeeded))
+    } else {
+        Err(format!(
+            "{}: {}, ошибок {}. {}",
+            verb,
+            result.succeeded,
+            result.failed,
+            result.errors.join("; ")
+        ))
+    }
+}
+
+/// Пакетные действия по списку SRID / номеров отправлений, вставленному из буфера обмена.
+#[component]
+pub fn BatchByIdsDialog(show: RwSignal<bool>) -> impl IntoView {
+    let input = RwSignal::new(String::new());
+    let tag = RwSignal::new(String::new());
+    let found = RwSignal::<Option<BulkLookupResponse>>::new(None);
+    let busy = RwSignal::new(false);
+    let (result, set_result) = signal::<Option<Result<String, String>>>(None);
+
+    let parsed_count = Memo::new(move |_| input.with(|text| parse_id_list(text).len()));
+
+    let close = Callback::new(move |_: ()| {
+        input.set(String::new());
+        tag.set(String::new());
+        found.set(None);
+        set_result.set(None);
+        busy.set(false);
+        show.set(false);
+    });
+
+    let run_lookup = move || {
+        let ids = parse_id_list(&input.get_untracked());
+        if ids.is_empty() {
+            set_result.set(Some(
+                Err("Вставьте номера — по одному в строке".to_string()),
+            ));
+            return;
+        }
+        if ids.len() > BULK_LOOKUP_MAX_IDS {
+            set_result.set(Some(Err(format!(
+                "В списке {} номеров, максимум {}",
+                ids.len(),
+                BULK_LOOKUP_MAX_IDS
+            ))));
+            return;
+        }
+        busy.set(true);
+        spawn_local(async move {
+            match api::lookup_documents(ids).await {
+                Ok(response) => found.set(Some(response)),
+                Err(err) => set_result.set(Some(Err(err))),
+            }
+            busy.set(false);
+        });
+    };
+
+    let lookup = move |_| {
+        set_result.set(None);
+        run_lookup();
+    };
+
+    let post = move |_| {
+        let documents = document_refs(&found.get_untracked());
+        let msg = format!("Провести найденные документы ({})?", documents.len());
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message(&msg).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        busy.set(true);
+        set_result.set(None);
+        spawn_local(async move {
+            let outcome = api::post_documents(documents).await;
+            busy.set(false);
+            set_result.set(Some(outcome.and_then(|r| action_message("Проведено", &r))));
+            run_lookup();
+        });
+    };
+
+    let apply_tag = move |_| {
+        let value = tag.get_untracked().trim().to_string();
+        if value.is_empty() {
+            set_result.set(Some(Err("Введите метку".to_string())));
+            return;
+        }
+        let documents = document_refs(&found.get_untracked());
+        busy.set(true);
+        set_result.set(None);
+        spawn_local(async move {
+            let outcome = api::tag_documents(documents, value).await;
+            busy.set(false);
+            set_result.set(Some(outcome.and_then(|r| action_message("Помечено", &r))));
+            run_lookup();
+        });
+    };
+
+    let export = move |_| {
+        let items = found.get_untracked().map(|r| r.items).unwrap_or_default();
+        if let Err(err) = export_to_excel(&items, "documents_by_ids.csv") {
+            set_result.set(Some(Err(err)));
+        }
+    };
+
+    let has_items = move || found.with(|r| r.as_ref().is_some_and(|r| !r.items.is_empty()));
+
+    view! {
+        <Show when=move || show.get() fallback=|| view! {}>
+            <ModalFrame
+                on_close=close
+                modal_style="max-width: 900px; width: 94vw;".to_string()
+            >
+                <div class="modal-header">
+                    <span class="modal-title">"Операции по списку номеров"</span>
+                </div>
+
+                <div class="modal-body" style="display: flex; flex-direction: column; gap: 12px;">
+                    <label>"SRID WB или номера отправлений Ozon — по одному в строке (можно вставить столбец из Excel)"</label>
+                    <textarea
+                        class="form__textarea"
+                        rows="8"
+                        prop:value=input
+                        on:input=move |ev| input.set(event_target_value(&ev))
+                    />
+                    <div style="display: flex; gap: 8px; align-items: center;">
+                        <button
+                            class="button button--primary"
+                            disabled=move || busy.get() || parsed_count.get() == 0
+                            on:click=lookup
+                        >
+                            {icon("search")} "Найти"
+                        </button>
+                        <span class="text-muted">{move || format!("Номеров в списке: {}", parsed_count.get())}</span>
+                    </div>
+
+                    {move || result.get().map(|outcome| match outcome {
+                        Ok(message) => view! { <div class="alert alert--success">{message}</div> }.into_any(),
+                        Err(err) => view! { <div class="alert alert--error">{err}</div> }.into_any(),
+                    })}
+
+                    {move || found.get().map(|r| {
+                        let not_found = r.not_found.clone();
+                        view! {
+                            <div class="text-muted">
+                                {format!("Найдено документов: {}, не найдено номеров: {}", r.items.len(), not_found.len())}
+                            </div>
+                            {(!not_found.is_empty()).then(|| view! {
+                                <div class="alert alert--error" style="max-height: 80px; overflow: auto;">
+                                    "Не найдены: " {not_found.join(", ")}
+                                </div>
+                            })}
+                            <div class="table-wrapper" style="max-height: 320px; overflow: auto;">
+                                <table class="table__data table--striped">
+                                    <thead class="table__head">
+                                        <tr>
+                                            <th class="table__header-cell">"Номер"</th>
+                                            <th class="table__header-cell">"Тип"</th>
+                                            <th class="table__header-cell">"Документ"</th>
+                                            <th class="table__header-cell">"Проведён"</th>
+                                            <th class="table__header-cell">"Метки"</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {r.items.into_iter().map(|item| view! {
+                                            <tr class="table__row">
+                                                <td class="table__cell">{item.document_no}</td>
+                                                <td class="table__cell">{tab_label_for_key(&item.entity_type)}</td>
+                                                <td class="table__cell">{item.description}</td>
+                                                <td class="table__cell">
+                                                    {if item.is_posted {
+                                                        view! { <span class="badge badge--success">"Да"</span> }.into_any()
+                                                    } else {
+                                                        view! { <span class="badge badge--neutral">"Нет"</span> }.into_any()
+                                                    }}
+                                                </td>
+                                                <td class="table__cell">{item.tags.join(", ")}</td>
+                                            </tr>
+                                        }).collect_view()}
+                                    </tbody>
+                                </table>
+                            </div>
+                        }
+                    })}
+                </div>
+
+                <div class="modal-footer">
+                    <input
+                        type="text"
+                        class="form__input"
+                        style="max-width: 200px;"
+                        placeholder="Метка"
+                        prop:value=tag
+                        on:input=move |ev| tag.set(event_target_value(&ev))
+                    />
+                    <button class="button button--secondary" disabled=move || busy.get() || !has_items() on:click=apply_tag>
+                        {icon("tag")} "Пометить"
+                    </button>
+                    <button class="button button--secondary" disabled=move || busy.get() || !has_items() on:click=export>
+                        {icon("download")} "Экспорт"
+                    </button>
+                    <button class="button button--primary" disabled=move || busy.get() || !has_items() on:click=post>
+                        {icon("check")} "Провести"
+                    </button>
+                    <button class="button button--secondary" on:click=move |_| close.run(())>
+                        "Закрыть"
+                    </button>
+                </div>
+            </ModalFrame>
+        </Show>
+    }
+}
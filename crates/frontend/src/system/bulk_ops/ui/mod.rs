@@ -0,0 +1,242 @@
+mod batch_by_ids;
+
+use contracts::system::bulk_ops::{BulkOperationDto, BulkOperationListResponse};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::Input;
+
+use crate::layout::tabs::tab_labels::tab_label_for_key;
+use crate::shared::date_utils::format_datetime_utc_local;
+use crate::shared::icons::icon;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::auth::guard::RequireAdmin;
+use crate::system::bulk_ops::api;
+
+use batch_by_ids::BatchByIdsDialog;
+
+#[component]
+pub fn BulkOperationsPage() -> impl IntoView {
+    view! {
+        <RequireAdmin>
+            <BulkOperationsContent />
+        </RequireAdmin>
+    }
+}
+
+#[component]
+fn BulkOperationsContent() -> impl IntoView {
+    let data = RwSignal::<Option<BulkOperationListResponse>>::new(None);
+    let loading = RwSignal::new(false);
+    let action_busy = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let notice = RwSignal::<Option<String>>::new(None);
+    let window_input = RwSignal::new(String::new());
+    let show_batch_dialog = RwSignal::new(false);
+
+    let reload = Callback::new(move |_| {
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_operations().await {
+                Ok(next) => {
+                    window_input.set(next.undo_window_minutes.to_string());
+                    data.set(Some(next));
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+    });
+
+    let save_window = move |_| {
+        let Ok(minutes) = window_input.get_untracked().trim().parse::<i64>() else {
+            error.set(Some("Окно отмены — целое число минут".to_string()));
+            return;
+        };
+        action_busy.set(true);
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::update_undo_window(minutes).await {
+                Ok(saved) => {
+                    notice.set(Some(format!("Окно отмены: {} мин", saved.minutes)));
+                    reload.run(());
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            action_busy.set(false);
+        });
+    };
+
+    let undo = move |op: BulkOperationDto| {
+        let msg = format!(
+            "Отменить операцию «{}» ({}, документов: {})? Документы будут проведены повторно.",
+            op_kind_label(&op.op_kind),
+            tab_label_for_key(&op.entity_type),
+            op.item_count
+        );
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message(&msg).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+
+        action_busy.set(true);
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::undo_operation(&op.id).await {
+                Ok(result) => notice.set(Some(format!(
+                    "Операция отменена: восстановлено документов {}",
+                    result.restored
+                ))),
+                Err(err) => error.set(Some(err)),
+            }
+            reload.run(());
+            action_busy.set(false);
+        });
+    };
+
+    view! {
+        <PageFrame page_id="sys_bulk_operations--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"История операций"</h1>
+                    <p class="page__subtitle">"Массовые отмены проведения. В пределах окна операцию можно отменить — документы будут проведены повторно."</p>
+                </div>
+                <div class="page__header-right">
+                    <button class="button button--secondary" on:click=move |_| show_batch_dialog.set(true)>
+                        {icon("list")} "Операции по списку номеров"
+                    </button>
+                    <button
+                        class="button button--secondary"
+                        disabled=move || loading.get()
+                        on:click=move |_| reload.run(())
+                    >
+                        {icon("refresh-cw")}
+                        {move || if loading.get() { "Обновление данных..." } else { "Обновить данные" }}
+                    </button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+                {move || notice.get().map(|msg| view! {
+                    <div class="alert alert--success">{msg}</div>
+                })}
+
+                <section class="raw-storage__section">
+                    <div class="raw-storage__list">
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Окно отмены, минут (0 — отмена выключена)"</span>
+                            <div class="raw-storage__list-action-group">
+                                <Input class="raw-storage__days-input" value=window_input />
+                                <button class="button button--secondary" disabled=move || action_busy.get() on:click=save_window>
+                                    {icon("save")} "Сохранить"
+                                </button>
+                            </div>
+                        </div>
+                    </div>
+                </section>
+
+                <section class="raw-storage__section">
+                    <div class="table-wrapper">
+                        <table class="table__data table--striped">
+                            <thead class="table__head">
+                                <tr>
+                                    <th class="table__header-cell">"Время"</th>
+                                    <th class="table__header-cell">"Операция"</th>
+                                    <th class="table__header-cell">"Документы"</th>
+                                    <th class="table__header-cell" style="text-align: right;">"Кол-во"</th>
+                                    <th class="table__header-cell">"Статус"</th>
+                                    <th class="table__header-cell">"Отмена до"</th>
+                                    <th class="table__header-cell"></th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {move || {
+                                    data.get()
+                                        .map(|d| d.rows)
+                                        .unwrap_or_default()
+                                        .into_iter()
+                                        .map(|op| {
+                                            let can_undo = op.can_undo;
+                                            let status_title = op.undo_error.clone().unwrap_or_default();
+                                            let op_for_undo = op.clone();
+                                            view! {
+                                                <tr class="table__row">
+                                                    <td class="table__cell">{format_datetime_utc_local(&op.created_at, "%d.%m.%Y %H:%M:%S")}</td>
+                                                    <td class="table__cell">{op_kind_label(&op.op_kind)}</td>
+                                                    <td class="table__cell">{tab_label_for_key(&op.entity_type)}</td>
+                                                    <td class="table__cell" style="text-align: right;">{op.item_count}</td>
+                                                    <td class="table__cell" title=status_title>
+                                                        <span class=status_badge_class(&op.status)>{status_label(&op.status)}</span>
+                                                    </td>
+                                                    <td class="table__cell">
+                                                        {if op.status == "done" {
+                                                            format_datetime_utc_local(&op.undo_deadline, "%d.%m.%Y %H:%M")
+                                                        } else {
+                                                            op.undone_at
+                                                                .as_deref()
+                                                                .map(|ts| format!("отменена {}", format_datetime_utc_local(ts, "%d.%m.%Y %H:%M")))
+                                                                .unwrap_or_default()
+                                                        }}
+                                                    </td>
+                                                    <td class="table__cell">
+                                                        {can_undo.then(|| view! {
+                                                            <button
+                                                                class="button button--secondary"
+                                                                disabled=move || action_busy.get()
+                                                                on:click=move |_| undo(op_for_undo.clone())
+                                                            >
+                                                                {icon("return")} "Отменить"
+                                                            </button>
+                                                        })}
+                                                    </td>
+                                                </tr>
+                                            }
+                                        })
+                                        .collect_view()
+                                }}
+                            </tbody>
+                        </table>
+                    </div>
+                </section>
+            </div>
+
+            <BatchByIdsDialog show=show_batch_dialog />
+        </PageFrame>
+    }
+}
+
+fn op_kind_label(op_kind: &str) -> &'static str {
+    match op_kind {
+        "unpost" => "Отмена проведения",
+        _ => "Операция",
+    }
+}
+
+fn status_label(status: &str) -> &'static str {
+    match status {
+        "done" => "Выполнена",
+        "undoing" => "Отменяется",
+        "undone" => "Отменена",
+        _ => "—",
+    }
+}
+
+fn status_badge_class(status: &str) -> &'static str {
+    match status {
+        "done" => "badge badge--neutral",
+        "undone" => "badge badge--success",
+        _ => "badge badge--neutral",
+    }
+}
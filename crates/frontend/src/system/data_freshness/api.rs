@@ -0,0 +1,33 @@
+use contracts::system::data_freshness::{DataFreshnessDto, DataFreshnessSlaSettings};
+
+use crate::shared::api_client::{self, ApiError};
+
+/// Текст ошибки для пользователя: 400 отдаёт причину в теле.
+fn user_error(err: ApiError) -> String {
+    match err {
+        ApiError::Status {
+            status: 400,
+            message,
+            ..
+        } if !message.is_empty() => message,
+        err => err.to_string(),
+    }
+}
+
+pub async fn fetch_all() -> Result<Vec<DataFreshnessDto>, String> {
+    api_client::get_json("/api/system/data-freshness")
+        .await
+        .map_err(user_error)
+}
+
+pub async fn fetch_sla() -> Result<DataFreshnessSlaSettings, String> {
+    api_client::get_json("/api/sys/data-freshness/sla")
+        .await
+        .map_err(user_error)
+}
+
+pub async fn save_sla(settings: &DataFreshnessSlaSettings) -> Result<(), String> {
+    api_client::put_json("/api/sys/data-freshness/sla", settings)
+        .await
+        .map_err(user_error)
+}
@@ -0,0 +1,189 @@
+use contracts::system::data_freshness::{
+    DataFreshnessDto, DataFreshnessSlaSettings, FreshnessDataset,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::collections::BTreeMap;
+
+use crate::shared::components::ui::badge::Badge;
+use crate::shared::date_utils::format_datetime_utc_local;
+use crate::shared::icons::icon;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::auth::guard::RequireAdmin;
+use crate::system::data_freshness::api;
+
+/// «Свежесть данных»: последний импорт каждого источника и его SLA.
+#[component]
+pub fn DataFreshnessPage() -> impl IntoView {
+    view! {
+        <RequireAdmin>
+            <DataFreshnessContent />
+        </RequireAdmin>
+    }
+}
+
+#[component]
+fn DataFreshnessContent() -> impl IntoView {
+    let items = RwSignal::<Vec<DataFreshnessDto>>::new(Vec::new());
+    // Код источника → введённое SLA, часов; пусто — значение по умолчанию
+    let sla_inputs = RwSignal::<BTreeMap<String, String>>::new(BTreeMap::new());
+    let loading = RwSignal::new(false);
+    let saving = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let notice = RwSignal::<Option<String>>::new(None);
+
+    let reload = Callback::new(move |_| {
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_all().await {
+                Ok(next) => items.set(next),
+                Err(err) => error.set(Some(err)),
+            }
+            match api::fetch_sla().await {
+                Ok(settings) => sla_inputs.set(
+                    settings
+                        .sla_hours
+                        .into_iter()
+                        .map(|(code, hours)| (code, hours.to_string()))
+                        .collect(),
+                ),
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+    });
+
+    let save = move |_| {
+        let mut settings = DataFreshnessSlaSettings::default();
+        for (code, value) in sla_inputs.get_untracked() {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            let Ok(hours) = value.parse::<i64>() else {
+                let label = FreshnessDataset::from_code(&code)
+                    .map(|d| d.label())
+                    .unwrap_or_default();
+                error.set(Some(format!(
+                    "{}: SLA должно быть целым числом часов",
+                    label
+                )));
+                return;
+            };
+            settings.sla_hours.insert(code, hours);
+        }
+        saving.set(true);
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::save_sla(&settings).await {
+                Ok(()) => {
+                    notice.set(Some("SLA сохранены".to_string()));
+                    reload.run(());
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            saving.set(false);
+        });
+    };
+
+    view! {
+        <PageFrame page_id="sys_data_freshness--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Свежесть данных"</h1>
+                    <p class="page__subtitle">"Последний успешный импорт по каждому источнику. Списки и дашборды показывают бейдж «данные на …» и подсвечивают его, если импорт старше SLA."</p>
+                </div>
+                <div class="page__header-right">
+                    <button
+                        class="button button--secondary"
+                        disabled=move || loading.get()
+                        on:click=move |_| reload.run(())
+                    >
+                        {icon("refresh-cw")}
+                        {move || if loading.get() { "Обновление данных..." } else { "Обновить данные" }}
+                    </button>
+                    <button class="button button--primary" disabled=move || saving.get() on:click=save>
+                        {icon("check")} "Сохранить SLA"
+                    </button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+                {move || notice.get().map(|msg| view! {
+                    <div class="alert alert--success">{msg}</div>
+                })}
+
+                <section class="raw-storage__section">
+                    <div class="table-wrapper">
+                        <table class="table__data table--striped">
+                            <thead class="table__head">
+                                <tr>
+                                    <th class="table__header-cell">"Источник"</th>
+                                    <th class="table__header-cell">"Последний импорт"</th>
+                                    <th class="table__header-cell">"SLA, ч"</th>
+                                    <th class="table__header-cell">"Состояние"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {move || {
+                                    items
+                                        .get()
+                                        .into_iter()
+                                        .map(|item| {
+                                            let code = item.dataset.code().to_string();
+                                            let code_for_input = code.clone();
+                                            let last = item
+                                                .last_import_at
+                                                .as_deref()
+                                                .map(|at| format_datetime_utc_local(at, "%d.%m.%Y %H:%M"))
+                                                .unwrap_or_else(|| "—".to_string());
+                                            let (variant, status) = if item.stale {
+                                                ("warning", "Устарели")
+                                            } else {
+                                                ("success", "Актуальны")
+                                            };
+                                            view! {
+                                                <tr class="table__row">
+                                                    <td class="table__cell">{item.label.clone()}</td>
+                                                    <td class="table__cell">{last}</td>
+                                                    <td class="table__cell">
+                                                        <input
+                                                            class="form__input"
+                                                            type="number"
+                                                            min="0"
+                                                            placeholder=item.dataset.default_sla_hours().to_string()
+                                                            prop:value=move || sla_inputs.with(|m| m.get(&code).cloned().unwrap_or_default())
+                                                            on:input=move |ev| {
+                                                                let value = event_target_value(&ev);
+                                                                sla_inputs.update(|m| {
+                                                                    m.insert(code_for_input.clone(), value);
+                                                                });
+                                                            }
+                                                        />
+                                                    </td>
+                                                    <td class="table__cell">
+                                                        <Badge variant=variant.to_string()>{status}</Badge>
+                                                    </td>
+                                                </tr>
+                                            }
+                                        })
+                                        .collect_view()
+                                }}
+                            </tbody>
+                        </table>
+                    </div>
+                </section>
+            </div>
+        </PageFrame>
+    }
+}
@@ -0,0 +1,100 @@
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+use contracts::system::description_templates::{
+    ApplyDescriptionTemplateResponse, DescriptionTemplateDto, DescriptionTemplateUpsertRequest,
+};
+use gloo_net::http::Request;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+fn template_url(entity_type: &str) -> String {
+    format!(
+        "{}/api/sys/description-templates/{}",
+        api_base(),
+        urlencoding::encode(entity_type)
+    )
+}
+
+pub async fn fetch_all() -> Result<Vec<DescriptionTemplateDto>, String> {
+    let response = Request::get(&format!("{}/api/sys/description-templates", api_base()))
+        .header("Authorization", &auth_header()?)
+        .header("Cache-Control", "no-cache")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch templates: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch templates: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse templates: {}", e))
+}
+
+pub async fn save(entity_type: &str, template: String) -> Result<(), String> {
+    let response = Request::put(&template_url(entity_type))
+        .header("Authorization", &auth_header()?)
+        .json(&DescriptionTemplateUpsertRequest { template })
+        .map_err(|e| format!("Failed to serialize template: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to save template: {}", e))?;
+
+    if response.status() == 400 {
+        return Err("Шаблон пустой, слишком длинный или содержит неизвестные поля".to_string());
+    }
+    if !response.ok() {
+        return Err(format!(
+            "Failed to save template: HTTP {}",
+            response.status()
+        ));
+    }
+    Ok(())
+}
+
+pub async fn reset(entity_type: &str) -> Result<(), String> {
+    let response = Request::delete(&template_url(entity_type))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reset template: {}", e))?;
+
+    if !response.ok() && response.status() != 404 {
+        return Err(format!(
+            "Failed to reset template: HTTP {}",
+            response.status()
+        ));
+    }
+    Ok(())
+}
+
+pub async fn apply(entity_type: &str) -> Result<ApplyDescriptionTemplateResponse, String> {
+    let response = Request::post(&format!("{}/apply", template_url(entity_type)))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to apply template: {}", e))?;
+
+    if response.status() == 400 {
+        return Err("Сначала сохраните шаблон".to_string());
+    }
+    if !response.ok() {
+        return Err(format!(
+            "Failed to apply template: HTTP {}",
+            response.status()
+        ));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse apply result: {}", e))
+}
@@ -0,0 +1,257 @@
+use contracts::system::description_templates::{
+    render_template, sample_values, unknown_placeholders, DescriptionTemplateDto, TemplateEntity,
+    TEMPLATE_ENTITIES,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::shared::date_utils::format_datetime_utc_local;
+use crate::shared::icons::icon;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::auth::guard::RequireAdmin;
+use crate::system::description_templates::api;
+
+#[component]
+pub fn DescriptionTemplatesPage() -> impl IntoView {
+    view! {
+        <RequireAdmin>
+            <DescriptionTemplatesContent />
+        </RequireAdmin>
+    }
+}
+
+#[component]
+fn DescriptionTemplatesContent() -> impl IntoView {
+    let items = RwSignal::<Vec<DescriptionTemplateDto>>::new(Vec::new());
+    let loading = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+
+    let reload = Callback::new(move |_| {
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_all().await {
+                Ok(next) => items.set(next),
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+    });
+
+    view! {
+        <PageFrame page_id="sys_description_templates--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Шаблоны описаний"</h1>
+                    <p class="page__subtitle">"Описание документа при импорте собирается по шаблону с полями вида {supplier_article}. Без шаблона импорт пишет описание по умолчанию."</p>
+                </div>
+                <div class="page__header-right">
+                    <button
+                        class="button button--secondary"
+                        disabled=move || loading.get()
+                        on:click=move |_| reload.run(())
+                    >
+                        {icon("refresh-cw")}
+                        {move || if loading.get() { "Обновление данных..." } else { "Обновить данные" }}
+                    </button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+
+                {move || {
+                    let items = items.get();
+                    TEMPLATE_ENTITIES
+                        .iter()
+                        .map(|entity| {
+                            let item = items
+                                .iter()
+                                .find(|item| item.entity_type == entity.entity_type)
+                                .cloned();
+                            view! { <TemplateEditor entity=entity item=item on_changed=reload /> }
+                        })
+                        .collect_view()
+                }}
+            </div>
+        </PageFrame>
+    }
+}
+
+#[component]
+fn TemplateEditor(
+    entity: &'static TemplateEntity,
+    item: Option<DescriptionTemplateDto>,
+    on_changed: Callback<()>,
+) -> impl IntoView {
+    let saved = item.as_ref().and_then(|item| item.template.clone());
+    let is_custom = saved.is_some();
+    let draft = RwSignal::new(saved.unwrap_or_else(|| entity.default_template.to_string()));
+    let busy = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let notice = RwSignal::<Option<String>>::new(None);
+
+    let changed = item
+        .as_ref()
+        .and_then(|item| {
+            item.updated_at.as_deref().map(|at| {
+                format!(
+                    "Изменён {} · {}",
+                    format_datetime_utc_local(at, "%d.%m.%Y %H:%M"),
+                    item.updated_by.clone().unwrap_or_default()
+                )
+            })
+        })
+        .unwrap_or_else(|| "Шаблон не задан — используется описание по умолчанию".to_string());
+
+    let preview = move || render_template(&draft.get(), &sample_values(entity));
+    let unknown = move || unknown_placeholders(entity, &draft.get());
+
+    let save = move |_| {
+        let template = draft.get_untracked();
+        busy.set(true);
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::save(entity.entity_type, template).await {
+                Ok(()) => on_changed.run(()),
+                Err(err) => error.set(Some(err)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let reset = move |_| {
+        busy.set(true);
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::reset(entity.entity_type).await {
+                Ok(()) => on_changed.run(()),
+                Err(err) => error.set(Some(err)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let apply = move |_| {
+        let msg = format!(
+            "Пересобрать описания всех документов «{}» по сохранённому шаблону?",
+            entity.label
+        );
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message(&msg).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        busy.set(true);
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::apply(entity.entity_type).await {
+                Ok(result) => notice.set(Some(format!(
+                    "Просмотрено документов: {}, обновлено описаний: {}",
+                    result.scanned, result.updated
+                ))),
+                Err(err) => error.set(Some(err)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let chips = entity
+        .placeholders
+        .iter()
+        .map(|(name, label, _)| {
+            let token = format!("{{{}}}", name);
+            let title = label.to_string();
+            view! {
+                <button
+                    class="button button--ghost button--small"
+                    title=title
+                    on:click=move |_| draft.update(|d| {
+                        if !d.is_empty() && !d.ends_with(' ') {
+                            d.push(' ');
+                        }
+                        d.push_str(&token);
+                    })
+                >
+                    {format!("{{{}}}", name)}
+                </button>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <section class="raw-storage__section">
+            <h2 class="raw-storage__section-title">{entity.label}</h2>
+            {move || error.get().map(|err| view! {
+                <div class="alert alert--error">{err}</div>
+            })}
+            {move || notice.get().map(|msg| view! {
+                <div class="alert alert--success">{msg}</div>
+            })}
+            <div class="raw-storage__list">
+                <div class="raw-storage__list-row">
+                    <span class="raw-storage__list-label">"Шаблон"</span>
+                    <input
+                        class="form__input"
+                        type="text"
+                        prop:value=move || draft.get()
+                        on:input=move |ev| draft.set(event_target_value(&ev))
+                    />
+                </div>
+                <div class="raw-storage__list-row">
+                    <span class="raw-storage__list-label">"Поля"</span>
+                    <div class="raw-storage__list-action-group">{chips}</div>
+                </div>
+                <div class="raw-storage__list-row">
+                    <span class="raw-storage__list-label">"Пример"</span>
+                    <span>{preview}</span>
+                </div>
+                {move || {
+                    let unknown = unknown();
+                    (!unknown.is_empty()).then(|| view! {
+                        <div class="alert alert--error">
+                            {format!("Неизвестные поля: {}", unknown.join(", "))}
+                        </div>
+                    })
+                }}
+                <div class="raw-storage__list-row">
+                    <span class="raw-storage__list-label">{changed}</span>
+                    <div class="raw-storage__list-action-group">
+                        <button
+                            class="button button--primary"
+                            disabled=move || busy.get() || !unknown().is_empty()
+                            on:click=save
+                        >
+                            {icon("check")} "Сохранить"
+                        </button>
+                        <button
+                            class="button button--secondary"
+                            disabled=move || busy.get() || !is_custom
+                            on:click=reset
+                        >
+                            {icon("x")} "По умолчанию"
+                        </button>
+                        <button
+                            class="button button--secondary"
+                            disabled=move || busy.get() || !is_custom
+                            on:click=apply
+                        >
+                            {icon("refresh-cw")} "Применить к документам"
+                        </button>
+                    </div>
+                </div>
+            </div>
+        </section>
+    }
+}
@@ -0,0 +1,15 @@
+use contracts::system::document_audit::DocumentAuditEntryDto;
+
+use crate::shared::api_client;
+
+pub async fn fetch_for_document(
+    entity_type: &str,
+    document_id: &str,
+) -> Result<Vec<DocumentAuditEntryDto>, String> {
+    api_client::get_json(&format!(
+        "/api/system/document-audit/{}/{}",
+        entity_type, document_id
+    ))
+    .await
+    .map_err(|e| e.to_string())
+}
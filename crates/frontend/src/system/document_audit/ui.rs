@@ -0,0 +1,125 @@
+use contracts::system::document_audit::{DocumentAuditAction, DocumentAuditEntryDto};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::shared::components::card_animated::CardAnimated;
+use crate::shared::components::ui::badge::Badge;
+use crate::shared::date_utils::format_datetime_utc_local;
+use crate::shared::icons::icon;
+use crate::system::document_audit::api;
+
+fn action_variant(action: DocumentAuditAction) -> &'static str {
+    match action {
+        DocumentAuditAction::Created => "primary",
+        DocumentAuditAction::Updated => "neutral",
+        DocumentAuditAction::Posted => "success",
+        DocumentAuditAction::Unposted => "warning",
+        DocumentAuditAction::Deleted => "error",
+    }
+}
+
+/// Вкладка «История» карточки документа: кто и когда создал, изменил, провёл или
+/// распровёл документ, со сводкой изменённых полей.
+#[component]
+pub fn DocumentHistoryPanel(
+    /// Агрегат документа (`a012_wb_sales`, `a015_wb_orders`, …)
+    entity_type: &'static str,
+    /// id документа; пока `None`, панель пустая
+    #[prop(into)]
+    document_id: Signal<Option<String>>,
+) -> impl IntoView {
+    let items = RwSignal::<Vec<DocumentAuditEntryDto>>::new(Vec::new());
+    let loading = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+
+    let reload = move || {
+        let Some(id) = document_id.get_untracked() else {
+            return;
+        };
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_for_document(entity_type, &id).await {
+                Ok(list) => items.set(list),
+                Err(e) => error.set(Some(e)),
+            }
+            loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| {
+        if document_id.get().is_some() {
+            reload();
+        }
+    });
+
+    view! {
+        <CardAnimated delay_ms=0 nav_id="document_history">
+            <div style="display: flex; align-items: center; justify-content: space-between;">
+                <h4 class="details-section__title">"История"</h4>
+                <button
+                    class="button button--secondary"
+                    disabled=move || loading.get()
+                    on:click=move |_| reload()
+                >
+                    {icon("refresh-cw")} "Обновить"
+                </button>
+            </div>
+
+            {move || error.get().map(|e| view! { <div class="alert alert--error">{e}</div> })}
+
+            <div class="table-wrapper">
+                <table class="table__data table--striped">
+                    <thead class="table__head">
+                        <tr>
+                            <th class="table__header-cell">"Когда"</th>
+                            <th class="table__header-cell">"Пользователь"</th>
+                            <th class="table__header-cell">"Действие"</th>
+                            <th class="table__header-cell">"Изменения"</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {move || {
+                            let list = items.get();
+                            if list.is_empty() {
+                                let text = if loading.get() { "Загрузка..." } else { "Записей нет" };
+                                return view! {
+                                    <tr class="table__row">
+                                        <td class="table__cell" colspan="4">{text}</td>
+                                    </tr>
+                                }
+                                .into_any();
+                            }
+                            list.into_iter()
+                                .map(|item| {
+                                    let user = item
+                                        .username
+                                        .clone()
+                                        .or(item.user_id.clone())
+                                        .unwrap_or_else(|| "—".to_string());
+                                    view! {
+                                        <tr class="table__row">
+                                            <td class="table__cell" style="white-space: nowrap;">
+                                                {format_datetime_utc_local(&item.created_at, "%d.%m.%Y %H:%M:%S")}
+                                            </td>
+                                            <td class="table__cell">{user}</td>
+                                            <td class="table__cell">
+                                                <Badge variant=action_variant(item.action).to_string()>
+                                                    {item.action.label()}
+                                                </Badge>
+                                            </td>
+                                            <td class="table__cell document-history__summary">
+                                                {item.summary.clone().unwrap_or_default()}
+                                            </td>
+                                        </tr>
+                                    }
+                                })
+                                .collect_view()
+                                .into_any()
+                        }}
+                    </tbody>
+                </table>
+            </div>
+        </CardAnimated>
+    }
+}
@@ -0,0 +1,48 @@
+use crate::shared::api_utils::api_base;
+use contracts::system::environment::{
+    DangerConfirmRequest, DangerConfirmResponse, EnvironmentInfoDto,
+};
+use gloo_net::http::Request;
+
+pub async fn fetch_info() -> Result<EnvironmentInfoDto, String> {
+    let response = Request::get(&format!("{}/api/system/environment", api_base()))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch environment: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch environment: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse environment: {}", e))
+}
+
+pub async fn request_token(req: &DangerConfirmRequest) -> Result<DangerConfirmResponse, String> {
+    let response = Request::post(&format!("{}/api/sys/environment/confirm", api_base()))
+        .json(req)
+        .map_err(|e| format!("Failed to serialize confirmation: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to confirm operation: {}", e))?;
+
+    if response.status() == 400 {
+        return Err("Подтверждение не принято: код окружения введён неверно".to_string());
+    }
+    if !response.ok() {
+        return Err(format!(
+            "Failed to confirm operation: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse confirmation: {}", e))
+}
@@ -0,0 +1,50 @@
+//! Подтверждение опасной операции перед запросом к защищённому эндпоинту.
+
+use contracts::system::environment::{danger_action, DangerConfirmRequest, EnvironmentMode};
+
+use super::api;
+
+/// Спрашивает подтверждение по режиму окружения и получает токен для заголовка
+/// `X-Confirm-Token`. `Ok(None)` — в dev токен не нужен; `Err` — пользователь
+/// отказался или сервер не выдал токен. `path` — путь без `api_base()`.
+pub async fn confirm_danger(
+    method: &str,
+    path: &str,
+    what: &str,
+) -> Result<Option<String>, String> {
+    let info = api::fetch_info().await?;
+    let typed_mode = match info.mode {
+        EnvironmentMode::Dev => return Ok(None),
+        EnvironmentMode::Staging => {
+            let msg = format!("{}: {}. Продолжить?", info.mode.label(), what);
+            let confirmed = web_sys::window()
+                .and_then(|w| w.confirm_with_message(&msg).ok())
+                .unwrap_or(false);
+            if !confirmed {
+                return Err("Операция отменена".to_string());
+            }
+            None
+        }
+        EnvironmentMode::Prod => {
+            let msg = format!(
+                "{}! {}.\nВведите «{}», чтобы подтвердить.",
+                info.mode.label(),
+                what,
+                info.mode.code()
+            );
+            let typed = web_sys::window()
+                .and_then(|w| w.prompt_with_message(&msg).ok())
+                .flatten();
+            match typed {
+                Some(typed) if !typed.trim().is_empty() => Some(typed),
+                _ => return Err("Операция отменена".to_string()),
+            }
+        }
+    };
+    let response = api::request_token(&DangerConfirmRequest {
+        action: danger_action(method, path),
+        typed_mode,
+    })
+    .await?;
+    Ok(response.token)
+}
@@ -0,0 +1,6 @@
+//! Режим окружения: цветной баннер над шапкой и подтверждение опасных операций
+//! (очисток, массовых удалений) на staging/prod.
+
+pub mod api;
+pub mod confirm;
+pub mod ui;
@@ -0,0 +1,36 @@
+use contracts::system::environment::EnvironmentInfoDto;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::api;
+
+/// Полоса над шапкой с режимом окружения: зелёная — dev, жёлтая — staging,
+/// красная — prod.
+#[component]
+pub fn EnvironmentBanner() -> impl IntoView {
+    let info = RwSignal::<Option<EnvironmentInfoDto>>::new(None);
+
+    spawn_local(async move {
+        match api::fetch_info().await {
+            Ok(next) => info.set(Some(next)),
+            Err(err) => leptos::logging::warn!("{}", err),
+        }
+    });
+
+    move || {
+        info.get().map(|info| {
+            let class = format!("env-banner env-banner--{}", info.mode.code());
+            let text = if info.name.trim().is_empty() {
+                format!("{} · {}", info.mode.label(), info.mode.code())
+            } else {
+                format!(
+                    "{} · {} · {}",
+                    info.mode.label(),
+                    info.mode.code(),
+                    info.name.trim()
+                )
+            };
+            view! { <div class=class>{text}</div> }
+        })
+    }
+}
@@ -0,0 +1,128 @@
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::system::export_profiles::{
+    ExportProfileDto, ExportProfileListResponse, ExportProfileSaveDto,
+};
+use contracts::system::exports::ExportJobDto;
+use gloo_net::http::{Request, Response};
+
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+/// Ошибки проверки (400) и квоты (429) сервер объясняет текстом.
+async fn error_text(response: Response, action: &str) -> String {
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    if (status == 400 || status == 429) && !text.is_empty() {
+        text
+    } else {
+        format!("Failed to {}: HTTP {}", action, status)
+    }
+}
+
+pub async fn fetch_profiles() -> Result<ExportProfileListResponse, String> {
+    let response = Request::get(&format!("{}/api/system/export-profiles", api_base()))
+        .header("Authorization", &auth_header()?)
+        .header("Cache-Control", "no-cache")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch export profiles: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "fetch export profiles").await);
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse export profiles: {}", e))
+}
+
+/// Создаёт профиль (`id = None`) или изменяет существующий.
+pub async fn save_profile(
+    id: Option<&str>,
+    dto: &ExportProfileSaveDto,
+) -> Result<ExportProfileDto, String> {
+    let request = match id {
+        Some(id) => Request::put(&format!("{}/api/system/export-profiles/{}", api_base(), id)),
+        None => Request::post(&format!("{}/api/system/export-profiles", api_base())),
+    };
+    let response = request
+        .header("Authorization", &auth_header()?)
+        .json(dto)
+        .map_err(|e| format!("Failed to serialize export profile: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to save export profile: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "save export profile").await);
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse export profile: {}", e))
+}
+
+pub async fn delete_profile(id: &str) -> Result<(), String> {
+    let response = Request::delete(&format!("{}/api/system/export-profiles/{}", api_base(), id))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to delete export profile: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "delete export profile").await);
+    }
+
+    Ok(())
+}
+
+/// Запуск профиля сейчас; файл появится в «Мои выгрузки».
+pub async fn run_profile(id: &str) -> Result<ExportJobDto, String> {
+    let response = Request::post(&format!(
+        "{}/api/system/export-profiles/{}/run",
+        api_base(),
+        id
+    ))
+    .header("Authorization", &auth_header()?)
+    .send()
+    .await
+    .map_err(|e| format!("Failed to run export profile: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "run export profile").await);
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse export job: {}", e))
+}
+
+/// Кабинеты маркетплейсов — для отбора профиля по кабинету.
+pub async fn fetch_connections() -> Result<Vec<ConnectionMP>, String> {
+    let response = Request::get(&format!("{}/api/connection_mp", api_base()))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Ошибка загрузки кабинетов: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Ошибка загрузки кабинетов: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Ошибка разбора кабинетов: {}", e))
+}
@@ -0,0 +1,576 @@
+use contracts::domain::a006_connection_mp::aggregate::ConnectionMP;
+use contracts::domain::common::AggregateId;
+use contracts::system::export_profiles::{
+    entity_info, ExportProfileDto, ExportProfileFilter, ExportProfileSaveDto,
+    EXPORT_PROFILE_ENTITIES,
+};
+use contracts::system::exports::ExportFileFormat;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::shared::date_utils::format_datetime_utc_local;
+use crate::shared::icons::icon;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::export_profiles::api;
+
+/// Ключ вкладки «Профили выгрузок».
+pub const EXPORT_PROFILES_TAB_KEY: &str = "sys_export_profiles";
+
+/// Расписание по умолчанию для нового профиля: по понедельникам в 07:00 (UTC).
+const DEFAULT_CRON: &str = "0 0 7 * * Mon";
+
+/// Поля редактора профиля.
+#[derive(Clone, Copy)]
+struct ProfileForm {
+    name: RwSignal<String>,
+    entity_type: RwSignal<String>,
+    columns: RwSignal<Vec<String>>,
+    tags: RwSignal<String>,
+    connection_id: RwSignal<String>,
+    period_days: RwSignal<String>,
+    posted_only: RwSignal<bool>,
+    format: RwSignal<ExportFileFormat>,
+    schedule_cron: RwSignal<String>,
+    schedule_enabled: RwSignal<bool>,
+}
+
+impl ProfileForm {
+    fn new() -> Self {
+        Self {
+            name: RwSignal::new(String::new()),
+            entity_type: RwSignal::new(String::new()),
+            columns: RwSignal::new(Vec::new()),
+            tags: RwSignal::new(String::new()),
+            connection_id: RwSignal::new(String::new()),
+            period_days: RwSignal::new(String::new()),
+            posted_only: RwSignal::new(false),
+            format: RwSignal::new(ExportFileFormat::default()),
+            schedule_cron: RwSignal::new(String::new()),
+            schedule_enabled: RwSignal::new(false),
+        }
+    }
+
+    /// Новый профиль: первый тип документов со всеми колонками.
+    fn reset(&self) {
+        let entity = &EXPORT_PROFILE_ENTITIES[0];
+        self.name.set(String::new());
+        self.entity_type.set(entity.entity_type.to_string());
+        self.columns
+            .set(entity.columns.iter().map(|c| c.key.to_string()).collect());
+        self.tags.set(String::new());
+        self.connection_id.set(String::new());
+        self.period_days.set("7".to_string());
+        self.posted_only.set(false);
+        self.format.set(ExportFileFormat::default());
+        self.schedule_cron.set(DEFAULT_CRON.to_string());
+        self.schedule_enabled.set(false);
+    }
+
+    fn load(&self, profile: &ExportProfileDto) {
+        self.name.set(profile.name.clone());
+        self.entity_type.set(profile.entity_type.clone());
+        self.columns.set(profile.columns.clone());
+        self.tags.set(profile.filter.tags.join(", "));
+        self.connection_id
+            .set(profile.filter.connection_id.clone().unwrap_or_default());
+        self.period_days.set(
+            profile
+                .filter
+                .period_days
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+        );
+        self.posted_only.set(profile.filter.posted_only);
+        self.format.set(profile.format);
+        self.schedule_cron
+            .set(profile.schedule_cron.clone().unwrap_or_default());
+        self.schedule_enabled.set(profile.schedule_enabled);
+    }
+
+    fn to_dto(self) -> Result<ExportProfileSaveDto, String> {
+        let period_days = match self.period_days.get_untracked().trim() {
+            "" => None,
+            text => Some(
+                text.parse::<i64>()
+                    .map_err(|_| "Период — целое число дней".to_string())?,
+            ),
+        };
+        let connection_id = self.connection_id.get_untracked();
+        let schedule_cron = self.schedule_cron.get_untracked().trim().to_string();
+        let dto = ExportProfileSaveDto {
+            name: self.name.get_untracked().trim().to_string(),
+            entity_type: self.entity_type.get_untracked(),
+            filter: ExportProfileFilter {
+                tags: self
+                    .tags
+                    .get_untracked()
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+                connection_id: (!connection_id.is_empty()).then_some(connection_id),
+                period_days,
+                posted_only: self.posted_only.get_untracked(),
+            },
+            columns: self.columns.get_untracked(),
+            format: self.format.get_untracked(),
+            schedule_cron: (!schedule_cron.is_empty()).then_some(schedule_cron),
+            schedule_enabled: self.schedule_enabled.get_untracked(),
+        };
+        dto.validate()?;
+        Ok(dto)
+    }
+}
+
+fn entity_label(entity_type: &str) -> String {
+    entity_info(entity_type)
+        .map(|e| e.label.to_string())
+        .unwrap_or_else(|| entity_type.to_string())
+}
+
+fn filter_summary(filter: &ExportProfileFilter) -> String {
+    let mut parts = Vec::new();
+    if !filter.tags.is_empty() {
+        parts.push(format!("метки: {}", filter.tags.join(", ")));
+    }
+    if let Some(days) = filter.period_days {
+        parts.push(format!("последние {} дн.", days));
+    }
+    if filter.connection_id.is_some() {
+        parts.push("один кабинет".to_string());
+    }
+    if filter.posted_only {
+        parts.push("только проведённые".to_string());
+    }
+    if parts.is_empty() {
+        "все документы".to_string()
+    } else {
+        parts.join("; ")
+    }
+}
+
+#[component]
+pub fn ExportProfilesPage() -> impl IntoView {
+    let items = RwSignal::<Vec<ExportProfileDto>>::new(Vec::new());
+    let connections = RwSignal::<Vec<ConnectionMP>>::new(Vec::new());
+    let loading = RwSignal::new(false);
+    let saving = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let notice = RwSignal::<Option<String>>::new(None);
+    // None — редактор закрыт; Some(None) — новый профиль; Some(Some(id)) — изменение
+    let editing = RwSignal::<Option<Option<String>>>::new(None);
+    let form = ProfileForm::new();
+
+    let reload = Callback::new(move |_| {
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_profiles().await {
+                Ok(response) => items.set(response.items),
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+        spawn_local(async move {
+            if let Ok(list) = api::fetch_connections().await {
+                connections.set(list);
+            }
+        });
+    });
+
+    let create = move |_| {
+        form.reset();
+        notice.set(None);
+        editing.set(Some(None));
+    };
+
+    let edit = move |profile: ExportProfileDto| {
+        form.load(&profile);
+        notice.set(None);
+        editing.set(Some(Some(profile.id)));
+    };
+
+    let save = move |_| {
+        let Some(id) = editing.get_untracked() else {
+            return;
+        };
+        let dto = match form.to_dto() {
+            Ok(dto) => dto,
+            Err(err) => {
+                error.set(Some(err));
+                return;
+            }
+        };
+        saving.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::save_profile(id.as_deref(), &dto).await {
+                Ok(_) => {
+                    editing.set(None);
+                    reload.run(());
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            saving.set(false);
+        });
+    };
+
+    let run = move |profile: ExportProfileDto| {
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::run_profile(&profile.id).await {
+                Ok(_) => {
+                    notice.set(Some(format!(
+                        "Профиль «{}» запущен — файл появится в «Мои выгрузки».",
+                        profile.name
+                    )));
+                    reload.run(());
+                }
+                Err(err) => error.set(Some(err)),
+            }
+        });
+    };
+
+    let remove = move |profile: ExportProfileDto| {
+        let msg = format!("Удалить профиль «{}»?", profile.name);
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message(&msg).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        spawn_local(async move {
+            match api::delete_profile(&profile.id).await {
+                Ok(()) => {
+                    if editing.get_untracked() == Some(Some(profile.id.clone())) {
+                        editing.set(None);
+                    }
+                    reload.run(());
+                }
+                Err(err) => error.set(Some(err)),
+            }
+        });
+    };
+
+    // При смене типа документов — все его колонки
+    let change_entity = move |entity_type: String| {
+        if let Some(entity) = entity_info(&entity_type) {
+            form.columns
+                .set(entity.columns.iter().map(|c| c.key.to_string()).collect());
+        }
+        form.entity_type.set(entity_type);
+    };
+
+    let toggle_column = move |key: &'static str, checked: bool| {
+        form.columns.update(|columns| {
+            columns.retain(|c| c != key);
+            if checked {
+                columns.push(key.to_string());
+            }
+        });
+    };
+
+    let editor = move || {
+        editing.get().map(|id| {
+            let title = if id.is_some() { "Изменение профиля" } else { "Новый профиль" };
+            view! {
+                <div class="card" style="margin-top: var(--spacing-md); padding: var(--spacing-md);">
+                    <h4 class="details-section__title">{title}</h4>
+
+                    <div class="form__group">
+                        <label class="form__label">"Название"</label>
+                        <input
+                            type="text"
+                            class="form__input"
+                            placeholder="Партнёр X — продажи за неделю"
+                            prop:value=move || form.name.get()
+                            on:input=move |ev| form.name.set(event_target_value(&ev))
+                        />
+                    </div>
+
+                    <div class="form__group">
+                        <label class="form__label">"Документы"</label>
+                        <select
+                            class="thaw-input"
+                            style="width: 100%;"
+                            prop:value=move || form.entity_type.get()
+                            on:change=move |ev| change_entity(event_target_value(&ev))
+                        >
+                            {EXPORT_PROFILE_ENTITIES
+                                .iter()
+                                .map(|e| view! { <option value=e.entity_type>{e.label}</option> })
+                                .collect_view()}
+                        </select>
+                    </div>
+
+                    <div class="form__group">
+                        <label class="form__label">"Колонки (порядок — как отмечены)"</label>
+                        <div style="display: flex; flex-wrap: wrap; gap: var(--spacing-xs) var(--spacing-md);">
+                            {move || {
+                                entity_info(&form.entity_type.get())
+                                    .map(|entity| {
+                                        entity
+                                            .columns
+                                            .iter()
+                                            .map(|column| {
+                                                let key = column.key;
+                                                view! {
+                                                    <label style="display: flex; gap: var(--spacing-xs); align-items: center;">
+                                                        <input
+                                                            type="checkbox"
+                                                            prop:checked=move || form.columns.with(|c| c.iter().any(|k| k == key))
+                                                            on:change=move |ev| toggle_column(key, event_target_checked(&ev))
+                                                        />
+                                                        <span>{column.label}</span>
+                                                    </label>
+                                                }
+                                            })
+                                            .collect_view()
+                                    })
+                            }}
+                        </div>
+                    </div>
+
+                    <h4 class="details-section__title">"Отбор"</h4>
+                    <div style="display: flex; flex-wrap: wrap; gap: var(--spacing-md);">
+                        <div class="form__group" style="flex: 1 1 240px;">
+                            <label class="form__label">"Метки (через запятую, любая из)"</label>
+                            <input
+                                type="text"
+                                class="form__input"
+                                placeholder="партнёр-x"
+                                prop:value=move || form.tags.get()
+                                on:input=move |ev| form.tags.set(event_target_value(&ev))
+                            />
+                        </div>
+                        <div class="form__group" style="flex: 1 1 240px;">
+                            <label class="form__label">"Кабинет"</label>
+                            <select
+                                class="thaw-input"
+                                style="width: 100%;"
+                                prop:value=move || form.connection_id.get()
+                                on:change=move |ev| form.connection_id.set(event_target_value(&ev))
+                            >
+                                <option value="">"Все кабинеты"</option>
+                                {move || {
+                                    connections
+                                        .get()
+                                        .into_iter()
+                                        .map(|connection| {
+                                            let id = connection.base.id.as_string();
+                                            view! { <option value=id>{connection.base.description}</option> }
+                                        })
+                                        .collect_view()
+                                }}
+                            </select>
+                        </div>
+                        <div class="form__group" style="flex: 0 1 160px;">
+                            <label class="form__label">"Последние N дней"</label>
+                            <input
+                                type="number"
+                                min="1"
+                                class="form__input"
+                                placeholder="за всё время"
+                                prop:value=move || form.period_days.get()
+                                on:input=move |ev| form.period_days.set(event_target_value(&ev))
+                            />
+                        </div>
+                    </div>
+                    <label style="display: flex; gap: var(--spacing-xs); align-items: center;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || form.posted_only.get()
+                            on:change=move |ev| form.posted_only.set(event_target_checked(&ev))
+                        />
+                        <span>"Только проведённые"</span>
+                    </label>
+
+                    <h4 class="details-section__title">"Файл и расписание"</h4>
+                    <div style="display: flex; flex-wrap: wrap; gap: var(--spacing-md); align-items: flex-end;">
+                        <div class="form__group" style="flex: 0 1 200px;">
+                            <label class="form__label">"Формат"</label>
+                            <select
+                                class="thaw-input"
+                                style="width: 100%;"
+                                prop:value=move || form.format.get().code()
+                                on:change=move |ev| {
+                                    if let Some(format) = ExportFileFormat::from_code(&event_target_value(&ev)) {
+                                        form.format.set(format);
+                                    }
+                                }
+                            >
+                                {ExportFileFormat::ALL
+                                    .into_iter()
+                                    .map(|f| view! { <option value=f.code()>{f.label()}</option> })
+                                    .collect_view()}
+                            </select>
+                        </div>
+                        <div class="form__group" style="flex: 1 1 240px;">
+                            <label class="form__label">"Расписание (cron: сек мин час день месяц день_недели, UTC)"</label>
+                            <input
+                                type="text"
+                                class="form__input"
+                                placeholder=DEFAULT_CRON
+                                prop:value=move || form.schedule_cron.get()
+                                on:input=move |ev| form.schedule_cron.set(event_target_value(&ev))
+                            />
+                        </div>
+                        <label class="form__group" style="display: flex; gap: var(--spacing-xs); align-items: center;">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || form.schedule_enabled.get()
+                                on:change=move |ev| form.schedule_enabled.set(event_target_checked(&ev))
+                            />
+                            <span>"Запускать по расписанию"</span>
+                        </label>
+                    </div>
+
+                    <div style="display: flex; gap: var(--spacing-sm); justify-content: flex-end;">
+                        <button class="button button--secondary" on:click=move |_| editing.set(None)>
+                            "Отмена"
+                        </button>
+                        <button class="button button--primary" disabled=move || saving.get() on:click=save>
+                            {icon("save")}
+                            {move || if saving.get() { "Сохранение..." } else { "Сохранить" }}
+                        </button>
+                    </div>
+                </div>
+            }
+        })
+    };
+
+    view! {
+        <PageFrame page_id="sys_export_profiles--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Профили выгрузок"</h1>
+                    <p class="page__subtitle">
+                        "Сохранённый отбор, колонки и формат файла. Запуск — по кнопке или по расписанию; файлы появляются в «Мои выгрузки»."
+                    </p>
+                </div>
+                <div class="page__header-right">
+                    <button class="button button--primary" on:click=create>
+                        {icon("plus")} "Новый профиль"
+                    </button>
+                    <button
+                        class="button button--secondary"
+                        disabled=move || loading.get()
+                        on:click=move |_| reload.run(())
+                    >
+                        {icon("refresh-cw")}
+                        {move || if loading.get() { "Обновление данных..." } else { "Обновить данные" }}
+                    </button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+                {move || notice.get().map(|text| view! {
+                    <div class="alert alert--success">{text}</div>
+                })}
+
+                <div class="table-wrapper">
+                    <table class="table__data table--striped">
+                        <thead class="table__head">
+                            <tr>
+                                <th class="table__header-cell">"Профиль"</th>
+                                <th class="table__header-cell">"Документы"</th>
+                                <th class="table__header-cell">"Отбор"</th>
+                                <th class="table__header-cell">"Формат"</th>
+                                <th class="table__header-cell">"Расписание"</th>
+                                <th class="table__header-cell">"Последний запуск"</th>
+                                <th class="table__header-cell"></th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <Show when=move || !loading.get() && items.with(|list| list.is_empty())>
+                                <tr class="table__row">
+                                    <td class="table__cell" colspan="7">"Профилей нет"</td>
+                                </tr>
+                            </Show>
+                            {move || {
+                                items
+                                    .get()
+                                    .into_iter()
+                                    .map(|profile| {
+                                        let schedule = match (&profile.schedule_cron, profile.schedule_enabled) {
+                                            (Some(cron), true) => format!(
+                                                "{} — следующий {}",
+                                                cron,
+                                                profile
+                                                    .next_run_at
+                                                    .as_deref()
+                                                    .map(|ts| format_datetime_utc_local(ts, "%d.%m.%Y %H:%M"))
+                                                    .unwrap_or_default()
+                                            ),
+                                            _ => "вручную".to_string(),
+                                        };
+                                        let last_run = profile
+                                            .last_run_at
+                                            .as_deref()
+                                            .map(|ts| format_datetime_utc_local(ts, "%d.%m.%Y %H:%M"))
+                                            .unwrap_or_default();
+                                        let last_error = profile.last_error.clone();
+                                        let for_run = profile.clone();
+                                        let for_edit = profile.clone();
+                                        let for_delete = profile.clone();
+                                        view! {
+                                            <tr class="table__row">
+                                                <td class="table__cell">{profile.name.clone()}</td>
+                                                <td class="table__cell">{entity_label(&profile.entity_type)}</td>
+                                                <td class="table__cell">{filter_summary(&profile.filter)}</td>
+                                                <td class="table__cell">{profile.format.label()}</td>
+                                                <td class="table__cell">{schedule}</td>
+                                                <td class="table__cell">
+                                                    {last_run}
+                                                    {last_error.map(|err| view! {
+                                                        " "
+                                                        <span class="badge badge--error" title=err>"Ошибка"</span>
+                                                    })}
+                                                </td>
+                                                <td class="table__cell" style="white-space: nowrap;">
+                                                    <button
+                                                        class="button button--secondary"
+                                                        title="Запустить сейчас"
+                                                        on:click=move |_| run(for_run.clone())
+                                                    >
+                                                        {icon("play")} "Запустить"
+                                                    </button>
+                                                    <button
+                                                        class="button button--secondary"
+                                                        title="Изменить"
+                                                        on:click=move |_| edit(for_edit.clone())
+                                                    >
+                                                        {icon("edit")}
+                                                    </button>
+                                                    <button
+                                                        class="button button--secondary"
+                                                        title="Удалить"
+                                                        on:click=move |_| remove(for_delete.clone())
+                                                    >
+                                                        {icon("trash")}
+                                                    </button>
+                                                </td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+
+                {editor}
+            </div>
+        </PageFrame>
+    }
+}
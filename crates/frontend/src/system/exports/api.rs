@@ -0,0 +1,83 @@
+use contracts::system::exports::{ExportJobDto, ExportJobListResponse};
+use gloo_net::http::Request;
+use serde::Serialize;
+
+use crate::shared::api_utils::api_base;
+use crate::shared::auth_download::download_authenticated_file;
+use crate::system::auth::storage;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+/// Ставит фоновую выгрузку: `path` — эндпоинт экспорта, `body` — фильтры списка.
+pub async fn start_export<T: Serialize>(path: &str, body: &T) -> Result<ExportJobDto, String> {
+    let response = Request::post(&format!("{}{}", api_base(), path))
+        .header("Authorization", &auth_header()?)
+        .json(body)
+        .map_err(|e| format!("Failed to serialize export request: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to start export: {}", e))?;
+
+    // 429 — исчерпана квота организации, сервер объясняет причину текстом
+    if response.status() == 429 {
+        return Err(response.text().await.unwrap_or_default());
+    }
+    if !response.ok() {
+        return Err(format!(
+            "Failed to start export: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse export job: {}", e))
+}
+
+pub async fn fetch_exports() -> Result<ExportJobListResponse, String> {
+    let response = Request::get(&format!("{}/api/system/exports", api_base()))
+        .header("Authorization", &auth_header()?)
+        .header("Cache-Control", "no-cache")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch exports: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch exports: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse exports: {}", e))
+}
+
+pub async fn delete_export(id: &str) -> Result<(), String> {
+    let response = Request::delete(&format!("{}/api/system/exports/{}", api_base(), id))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to delete export: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to delete export: HTTP {}",
+            response.status()
+        ));
+    }
+
+    Ok(())
+}
+
+pub async fn download_export(id: &str, fallback_filename: &str) -> Result<(), String> {
+    let url = format!("{}/api/system/exports/{}/download", api_base(), id);
+    download_authenticated_file(&url, fallback_filename).await
+}
@@ -0,0 +1,200 @@
+use contracts::system::exports::{ExportJobDto, ExportJobStatus, EXPORT_TTL_DAYS};
+use gloo_timers::future::TimeoutFuture;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::shared::date_utils::{format_bytes_compact, format_datetime_utc_local};
+use crate::shared::icons::icon;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::exports::api;
+
+/// Ключ вкладки «Мои выгрузки».
+pub const EXPORTS_TAB_KEY: &str = "sys_exports";
+
+/// Период обновления, пока есть незавершённые выгрузки.
+const POLL_INTERVAL_MS: u32 = 5_000;
+
+#[component]
+pub fn MyExportsPage() -> impl IntoView {
+    let items = RwSignal::<Vec<ExportJobDto>>::new(Vec::new());
+    let loading = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+
+    let reload = Callback::new(move |_| {
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_exports().await {
+                Ok(response) => items.set(response.items),
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+        spawn_local(async move {
+            loop {
+                TimeoutFuture::new(POLL_INTERVAL_MS).await;
+                // Вкладка закрыта — сигнал освобождён, опрос прекращается
+                let Some(pending) =
+                    items.try_with_untracked(|list| list.iter().any(|j| !j.status.is_finished()))
+                else {
+                    break;
+                };
+                if pending {
+                    if let Ok(response) = api::fetch_exports().await {
+                        items.try_set(response.items);
+                    }
+                }
+            }
+        });
+    });
+
+    let download = move |job: ExportJobDto| {
+        let fallback = job
+            .filename
+            .clone()
+            .unwrap_or_else(|| "export.csv".to_string());
+        spawn_local(async move {
+            if let Err(err) = api::download_export(&job.id, &fallback).await {
+                error.set(Some(format!("Скачивание: {err}")));
+            }
+        });
+    };
+
+    let remove = move |job: ExportJobDto| {
+        let msg = format!("Удалить выгрузку «{}»?", job.title);
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message(&msg).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        spawn_local(async move {
+            match api::delete_export(&job.id).await {
+                Ok(()) => reload.run(()),
+                Err(err) => error.set(Some(err)),
+            }
+        });
+    };
+
+    view! {
+        <PageFrame page_id="sys_exports--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Мои выгрузки"</h1>
+                    <p class="page__subtitle">
+                        {format!(
+                            "Тяжёлые выгрузки формируются в фоне — по готовности придёт уведомление. Файлы хранятся {} дн.",
+                            EXPORT_TTL_DAYS
+                        )}
+                    </p>
+                </div>
+                <div class="page__header-right">
+                    <button
+                        class="button button--secondary"
+                        disabled=move || loading.get()
+                        on:click=move |_| reload.run(())
+                    >
+                        {icon("refresh-cw")}
+                        {move || if loading.get() { "Обновление данных..." } else { "Обновить данные" }}
+                    </button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+
+                <div class="table-wrapper">
+                    <table class="table__data table--striped">
+                        <thead class="table__head">
+                            <tr>
+                                <th class="table__header-cell">"Создана"</th>
+                                <th class="table__header-cell">"Выгрузка"</th>
+                                <th class="table__header-cell">"Статус"</th>
+                                <th class="table__header-cell" style="text-align: right;">"Строк"</th>
+                                <th class="table__header-cell" style="text-align: right;">"Размер"</th>
+                                <th class="table__header-cell">"Доступна до"</th>
+                                <th class="table__header-cell"></th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <Show when=move || !loading.get() && items.with(|list| list.is_empty())>
+                                <tr class="table__row">
+                                    <td class="table__cell" colspan="7">"Выгрузок нет"</td>
+                                </tr>
+                            </Show>
+                            {move || {
+                                items
+                                    .get()
+                                    .into_iter()
+                                    .map(|job| {
+                                        let is_done = job.status == ExportJobStatus::Done;
+                                        let can_delete = job.status.is_finished();
+                                        let job_for_download = job.clone();
+                                        let job_for_delete = job.clone();
+                                        view! {
+                                            <tr class="table__row">
+                                                <td class="table__cell">{format_datetime_utc_local(&job.created_at, "%d.%m.%Y %H:%M")}</td>
+                                                <td class="table__cell">{job.title.clone()}</td>
+                                                <td class="table__cell" title=job.error.clone().unwrap_or_default()>
+                                                    <span class=status_badge_class(job.status)>{job.status.label()}</span>
+                                                </td>
+                                                <td class="table__cell" style="text-align: right;">
+                                                    {job.row_count.map(|n| n.to_string()).unwrap_or_default()}
+                                                </td>
+                                                <td class="table__cell" style="text-align: right;">
+                                                    {job.size_bytes.map(|n| format_bytes_compact(n.max(0) as u64)).unwrap_or_default()}
+                                                </td>
+                                                <td class="table__cell">
+                                                    {job.expires_at
+                                                        .as_deref()
+                                                        .filter(|_| is_done)
+                                                        .map(|ts| format_datetime_utc_local(ts, "%d.%m.%Y %H:%M"))
+                                                        .unwrap_or_default()}
+                                                </td>
+                                                <td class="table__cell" style="white-space: nowrap;">
+                                                    {is_done.then(|| view! {
+                                                        <button
+                                                            class="button button--secondary"
+                                                            on:click=move |_| download(job_for_download.clone())
+                                                        >
+                                                            {icon("download")} "Скачать"
+                                                        </button>
+                                                    })}
+                                                    {can_delete.then(|| view! {
+                                                        <button
+                                                            class="button button--secondary"
+                                                            title="Удалить"
+                                                            on:click=move |_| remove(job_for_delete.clone())
+                                                        >
+                                                            {icon("trash")}
+                                                        </button>
+                                                    })}
+                                                </td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+            </div>
+        </PageFrame>
+    }
+}
+
+fn status_badge_class(status: ExportJobStatus) -> &'static str {
+    match status {
+        ExportJobStatus::Done => "badge badge--success",
+        ExportJobStatus::Failed => "badge badge--error",
+        ExportJobStatus::Queued | ExportJobStatus::Running => "badge badge--warning",
+        ExportJobStatus::Expired => "badge badge--neutral",
+    }
+}
@@ -0,0 +1,57 @@
+use contracts::system::external_refs::{
+    CreateExternalRefRequest, ExternalRefDto, ExternalRefFields,
+};
+
+use crate::shared::api_client::{self, ApiError};
+
+/// Текст ошибки для пользователя: 400 отдаёт причину в теле.
+fn user_error(err: ApiError) -> String {
+    match err {
+        ApiError::Status {
+            status: 400,
+            message,
+            ..
+        } if !message.is_empty() => message,
+        err => err.to_string(),
+    }
+}
+
+pub async fn fetch_for_document(
+    entity_type: &str,
+    document_id: &str,
+) -> Result<Vec<ExternalRefDto>, String> {
+    api_client::get_json(&format!(
+        "/api/external-refs?entity_type={}&document_id={}",
+        entity_type, document_id
+    ))
+    .await
+    .map_err(user_error)
+}
+
+pub async fn create(request: &CreateExternalRefRequest) -> Result<ExternalRefDto, String> {
+    api_client::post_json("/api/external-refs", request)
+        .await
+        .map_err(user_error)
+}
+
+pub async fn update(id: &str, fields: &ExternalRefFields) -> Result<(), String> {
+    api_client::put_json(&format!("/api/external-refs/{}", id), fields)
+        .await
+        .map_err(user_error)
+}
+
+pub async fn delete(id: &str) -> Result<(), String> {
+    api_client::delete(&format!("/api/external-refs/{}", id))
+        .await
+        .map_err(user_error)
+}
+
+/// Глобальный поиск по ключу, системе и URL.
+pub async fn search(text: &str) -> Result<Vec<ExternalRefDto>, String> {
+    api_client::get_json(&format!(
+        "/api/external-refs/search?q={}",
+        urlencoding::encode(text)
+    ))
+    .await
+    .map_err(user_error)
+}
@@ -0,0 +1,392 @@
+use contracts::system::external_refs::{
+    external_ref_document, CreateExternalRefRequest, ExternalRefDto, ExternalRefFields,
+    EXTERNAL_REF_SYSTEM_SUGGESTIONS,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::layout::global_context::AppGlobalContext;
+use crate::shared::components::card_animated::CardAnimated;
+use crate::shared::date_utils::format_datetime_utc_local;
+use crate::shared::icons::icon;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::external_refs::api;
+
+fn fmt_dt(iso: &str) -> String {
+    format_datetime_utc_local(iso, "%d.%m.%Y %H:%M")
+}
+
+fn url_cell(url: Option<String>) -> impl IntoView {
+    url.map(|url| {
+        view! {
+            <a href=url.clone() target="_blank" rel="noopener noreferrer">{url}</a>
+        }
+    })
+}
+
+/// Вкладка «Внешние ссылки» карточки документа: список, добавление, правка, удаление.
+#[component]
+pub fn ExternalRefsPanel(
+    /// Агрегат документа (`a012_wb_sales`, `a015_wb_orders`, `a032_wb_returns_claims`)
+    entity_type: &'static str,
+    /// id документа; пока `None`, панель пустая
+    #[prop(into)]
+    document_id: Signal<Option<String>>,
+) -> impl IntoView {
+    let items = RwSignal::<Vec<ExternalRefDto>>::new(Vec::new());
+    let loading = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let busy = RwSignal::new(false);
+    // id редактируемой ссылки; `None` — форма добавляет новую
+    let editing = RwSignal::<Option<String>>::new(None);
+    let system = RwSignal::new(String::new());
+    let key = RwSignal::new(String::new());
+    let url = RwSignal::new(String::new());
+
+    let reset_form = move || {
+        editing.set(None);
+        system.set(String::new());
+        key.set(String::new());
+        url.set(String::new());
+    };
+
+    let reload = move || {
+        let Some(id) = document_id.get_untracked() else {
+            return;
+        };
+        loading.set(true);
+        spawn_local(async move {
+            match api::fetch_for_document(entity_type, &id).await {
+                Ok(list) => items.set(list),
+                Err(e) => error.set(Some(e)),
+            }
+            loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| {
+        if document_id.get().is_some() {
+            reload();
+        }
+    });
+
+    let on_submit = move |_| {
+        let Some(document_id) = document_id.get_untracked() else {
+            return;
+        };
+        let fields = ExternalRefFields {
+            system: system.get_untracked(),
+            key: key.get_untracked(),
+            url: Some(url.get_untracked()),
+        };
+        if let Err(e) = fields.normalized() {
+            error.set(Some(e));
+            return;
+        }
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            let result = match editing.get_untracked() {
+                Some(id) => api::update(&id, &fields).await,
+                None => api::create(&CreateExternalRefRequest {
+                    entity_type: entity_type.to_string(),
+                    document_id,
+                    fields,
+                })
+                .await
+                .map(|_| ()),
+            };
+            match result {
+                Ok(()) => {
+                    reset_form();
+                    reload();
+                }
+                Err(e) => error.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let on_edit = move |item: ExternalRefDto| {
+        error.set(None);
+        system.set(item.system);
+        key.set(item.key);
+        url.set(item.url.unwrap_or_default());
+        editing.set(Some(item.id));
+    };
+
+    let on_delete = move |item: ExternalRefDto| {
+        let msg = format!("Удалить ссылку {} {}?", item.system, item.key);
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message(&msg).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        busy.set(true);
+        spawn_local(async move {
+            match api::delete(&item.id).await {
+                Ok(()) => {
+                    if editing.get_untracked().as_deref() == Some(item.id.as_str()) {
+                        reset_form();
+                    }
+                    reload();
+                }
+                Err(e) => error.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let datalist_id = format!("external-ref-systems-{}", entity_type);
+
+    view! {
+        <CardAnimated delay_ms=0 nav_id="external_refs">
+            <h4 class="details-section__title">"Внешние ссылки"</h4>
+            <p class="text-muted">
+                "Номера этого документа в других системах: претензия WB, документ 1С/ERP и т.п. По ключу документ находится глобальным поиском."
+            </p>
+
+            {move || error.get().map(|e| view! { <div class="alert alert--error">{e}</div> })}
+
+            <div class="table-wrapper">
+                <table class="table__data table--striped">
+                    <thead class="table__head">
+                        <tr>
+                            <th class="table__header-cell">"Система"</th>
+                            <th class="table__header-cell">"Ключ"</th>
+                            <th class="table__header-cell">"URL"</th>
+                            <th class="table__header-cell">"Добавил"</th>
+                            <th class="table__header-cell"></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {move || {
+                            let list = items.get();
+                            if list.is_empty() {
+                                let text = if loading.get() { "Загрузка..." } else { "Ссылок нет" };
+                                return view! {
+                                    <tr class="table__row">
+                                        <td class="table__cell" colspan="5">{text}</td>
+                                    </tr>
+                                }
+                                .into_any();
+                            }
+                            list.into_iter()
+                                .map(|item| {
+                                    let for_edit = item.clone();
+                                    let for_delete = item.clone();
+                                    view! {
+                                        <tr class="table__row">
+                                            <td class="table__cell">{item.system.clone()}</td>
+                                            <td class="table__cell"><strong>{item.key.clone()}</strong></td>
+                                            <td class="table__cell">{url_cell(item.url.clone())}</td>
+                                            <td class="table__cell">
+                                                {format!("{} · {}", item.created_by, fmt_dt(&item.created_at))}
+                                            </td>
+                                            <td class="table__cell" style="white-space: nowrap;">
+                                                <button
+                                                    class="button button--secondary"
+                                                    title="Изменить"
+                                                    disabled=move || busy.get()
+                                                    on:click=move |_| on_edit(for_edit.clone())
+                                                >
+                                                    {icon("edit")}
+                                                </button>
+                                                <button
+                                                    class="button button--secondary"
+                                                    title="Удалить"
+                                                    disabled=move || busy.get()
+                                                    on:click=move |_| on_delete(for_delete.clone())
+                                                >
+                                                    {icon("trash")}
+                                                </button>
+                                            </td>
+                                        </tr>
+                                    }
+                                })
+                                .collect_view()
+                                .into_any()
+                        }}
+                    </tbody>
+                </table>
+            </div>
+
+            <datalist id=datalist_id.clone()>
+                {EXTERNAL_REF_SYSTEM_SUGGESTIONS
+                    .iter()
+                    .map(|s| view! { <option value=*s></option> })
+                    .collect_view()}
+            </datalist>
+            <div style="display: flex; gap: var(--spacing-sm); align-items: center; flex-wrap: wrap; margin-top: var(--spacing-md);">
+                <input
+                    class="form__input"
+                    placeholder="Система"
+                    list=datalist_id
+                    prop:value=move || system.get()
+                    on:input=move |ev| system.set(event_target_value(&ev))
+                />
+                <input
+                    class="form__input"
+                    placeholder="Ключ"
+                    prop:value=move || key.get()
+                    on:input=move |ev| key.set(event_target_value(&ev))
+                />
+                <input
+                    class="form__input"
+                    placeholder="URL (необязательно)"
+                    prop:value=move || url.get()
+                    on:input=move |ev| url.set(event_target_value(&ev))
+                />
+                <button
+                    class="button button--primary"
+                    disabled=move || busy.get() || document_id.get().is_none()
+                    on:click=on_submit
+                >
+                    {move || if editing.get().is_some() {
+                        view! { {icon("check")} "Сохранить" }.into_any()
+                    } else {
+                        view! { {icon("plus")} "Добавить" }.into_any()
+                    }}
+                </button>
+                <Show when=move || editing.get().is_some()>
+                    <button class="button button--secondary" on:click=move |_| reset_form()>
+                        "Отмена"
+                    </button>
+                </Show>
+            </div>
+        </CardAnimated>
+    }
+}
+
+/// Глобальный поиск документа по внешней ссылке.
+#[component]
+pub fn ExternalRefsSearchPage() -> impl IntoView {
+    let tabs_store =
+        leptos::context::use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
+    let query = RwSignal::new(String::new());
+    let items = RwSignal::<Vec<ExternalRefDto>>::new(Vec::new());
+    let searched = RwSignal::new(false);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+
+    let run_search = move || {
+        let text = query.get_untracked().trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::search(&text).await {
+                Ok(list) => items.set(list),
+                Err(e) => error.set(Some(e)),
+            }
+            searched.set(true);
+            loading.set(false);
+        });
+    };
+
+    let open_document = move |item: &ExternalRefDto| {
+        if let Some(doc) = external_ref_document(&item.entity_type) {
+            tabs_store.open_tab(
+                &format!("{}{}", doc.tab_prefix, item.document_id),
+                &format!("{} {}", doc.label, item.key),
+            );
+        }
+    };
+
+    view! {
+        <PageFrame page_id="sys_external_refs--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Внешние ссылки"</h1>
+                    <p class="page__subtitle">
+                        "Поиск документа по номеру во внешней системе: претензия WB, документ 1С/ERP, URL."
+                    </p>
+                </div>
+            </div>
+
+            <div class="page__content">
+                <div style="display: flex; gap: var(--spacing-sm); margin-bottom: var(--spacing-md);">
+                    <input
+                        class="form__input"
+                        placeholder="Номер, система или часть URL"
+                        prop:value=move || query.get()
+                        on:input=move |ev| query.set(event_target_value(&ev))
+                        on:keydown=move |ev: web_sys::KeyboardEvent| {
+                            if ev.key() == "Enter" {
+                                run_search();
+                            }
+                        }
+                    />
+                    <button
+                        class="button button--primary"
+                        disabled=move || loading.get()
+                        on:click=move |_| run_search()
+                    >
+                        {icon("search")} "Найти"
+                    </button>
+                </div>
+
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+
+                <div class="table-wrapper">
+                    <table class="table__data table--striped">
+                        <thead class="table__head">
+                            <tr>
+                                <th class="table__header-cell">"Система"</th>
+                                <th class="table__header-cell">"Ключ"</th>
+                                <th class="table__header-cell">"Документ"</th>
+                                <th class="table__header-cell">"URL"</th>
+                                <th class="table__header-cell">"Изменена"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {move || {
+                                let list = items.get();
+                                if list.is_empty() {
+                                    let text = if searched.get() { "Ничего не найдено" } else { "Введите запрос" };
+                                    return view! {
+                                        <tr class="table__row">
+                                            <td class="table__cell" colspan="5">{text}</td>
+                                        </tr>
+                                    }
+                                    .into_any();
+                                }
+                                list.into_iter()
+                                    .map(|item| {
+                                        let doc_label = external_ref_document(&item.entity_type)
+                                            .map(|d| d.label)
+                                            .unwrap_or("—");
+                                        let for_open = item.clone();
+                                        view! {
+                                            <tr class="table__row">
+                                                <td class="table__cell">{item.system.clone()}</td>
+                                                <td class="table__cell"><strong>{item.key.clone()}</strong></td>
+                                                <td class="table__cell">
+                                                    <a href="#" on:click=move |ev| {
+                                                        ev.prevent_default();
+                                                        open_document(&for_open);
+                                                    }>
+                                                        {doc_label}
+                                                    </a>
+                                                </td>
+                                                <td class="table__cell">{url_cell(item.url.clone())}</td>
+                                                <td class="table__cell">{fmt_dt(&item.updated_at)}</td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()
+                                    .into_any()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+            </div>
+        </PageFrame>
+    }
+}
@@ -1,11 +1,34 @@
 pub mod access;
+pub mod announcements;
 pub mod audit;
 pub mod auth;
+pub mod branding;
+pub mod bulk_import;
+pub mod bulk_ops;
+pub mod data_freshness;
+pub mod description_templates;
+pub mod document_audit;
+pub mod environment;
+pub mod export_profiles;
+pub mod exports;
+pub mod external_refs;
 pub mod favorites;
 pub mod history;
+pub mod notifications;
+pub mod operations;
+pub mod org_context;
 pub mod pages;
+pub mod presence;
+pub mod print_templates;
+pub mod projection_archive;
+pub mod projection_snapshots;
+pub mod quotas;
 pub mod raw_storage;
+pub mod return_dispositions;
 pub mod roles;
 pub mod s3;
+pub mod scheduled_posts;
+pub mod server_log;
+pub mod sso;
 pub mod tasks;
 pub mod users;
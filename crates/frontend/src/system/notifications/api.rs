@@ -0,0 +1,104 @@
+use contracts::system::notifications::{
+    NotificationInboxDto, NotificationPreferencesDto, NotificationPreferencesUpdate,
+};
+use gloo_net::http::Request;
+
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+pub async fn fetch_inbox() -> Result<NotificationInboxDto, String> {
+    let response = Request::get(&format!("{}/api/system/notifications", api_base()))
+        .header("Authorization", &auth_header()?)
+        .header("Cache-Control", "no-cache")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch notifications: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch notifications: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse notifications: {}", e))
+}
+
+pub async fn mark_all_read() -> Result<(), String> {
+    let response = Request::post(&format!("{}/api/system/notifications/read", api_base()))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to mark notifications read: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to mark notifications read: HTTP {}",
+            response.status()
+        ));
+    }
+    Ok(())
+}
+
+pub async fn fetch_preferences() -> Result<NotificationPreferencesDto, String> {
+    let response = Request::get(&format!(
+        "{}/api/system/notifications/preferences",
+        api_base()
+    ))
+    .header("Authorization", &auth_header()?)
+    .send()
+    .await
+    .map_err(|e| format!("Failed to fetch notification preferences: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch notification preferences: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse notification preferences: {}", e))
+}
+
+pub async fn save_preferences(
+    update: &NotificationPreferencesUpdate,
+) -> Result<NotificationPreferencesDto, String> {
+    let response = Request::put(&format!(
+        "{}/api/system/notifications/preferences",
+        api_base()
+    ))
+    .header("Authorization", &auth_header()?)
+    .json(update)
+    .map_err(|e| format!("Failed to serialize notification preferences: {}", e))?
+    .send()
+    .await
+    .map_err(|e| format!("Failed to save notification preferences: {}", e))?;
+
+    if response.status() == 400 {
+        let message = response.text().await.unwrap_or_default();
+        return Err(format!("Проверьте настройки: {}", message));
+    }
+    if !response.ok() {
+        return Err(format!(
+            "Failed to save notification preferences: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse saved notification preferences: {}", e))
+}
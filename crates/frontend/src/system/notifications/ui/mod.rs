@@ -0,0 +1,461 @@
+use chrono::{DateTime, FixedOffset};
+use contracts::system::notifications::{
+    NotificationChannel, NotificationDto, NotificationEvent, NotificationPreference,
+    NotificationPreferencesDto, NotificationPreferencesUpdate,
+};
+use gloo_timers::future::TimeoutFuture;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::Input;
+
+use crate::layout::global_context::AppGlobalContext;
+use crate::shared::icons::icon;
+use crate::shared::modal_frame::ModalFrame;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::exports::ui::EXPORTS_TAB_KEY;
+use crate::system::notifications::api;
+
+/// Ключ вкладки настроек уведомлений.
+pub const SETTINGS_TAB_KEY: &str = "sys_notification_settings";
+
+/// Период опроса счётчика непрочитанных.
+const POLL_INTERVAL_MS: u32 = 60_000;
+
+/// Moscow timezone offset (UTC+3).
+const MSK_OFFSET_SECONDS: i32 = 3 * 3600;
+
+/// "DD.MM HH:MM" по Москве (исходная строка, если не разобрать).
+fn created_label(raw: &str) -> String {
+    FixedOffset::east_opt(MSK_OFFSET_SECONDS)
+        .and_then(|offset| {
+            DateTime::parse_from_rfc3339(raw)
+                .ok()
+                .map(|dt| dt.with_timezone(&offset).format("%d.%m %H:%M").to_string())
+        })
+        .unwrap_or_else(|| raw.to_string())
+}
+
+#[component]
+pub fn NotificationsHeaderButton() -> impl IntoView {
+    let unread = RwSignal::new(0i64);
+    let modal_open = RwSignal::new(false);
+    let modal_closing = RwSignal::new(false);
+
+    let refresh = move || {
+        spawn_local(async move {
+            if let Ok(inbox) = api::fetch_inbox().await {
+                unread.set(inbox.unread);
+            }
+        });
+    };
+
+    Effect::new(move |_| {
+        refresh();
+        spawn_local(async move {
+            loop {
+                TimeoutFuture::new(POLL_INTERVAL_MS).await;
+                if let Ok(inbox) = api::fetch_inbox().await {
+                    unread.set(inbox.unread);
+                }
+            }
+        });
+    });
+
+    let close_modal = Callback::new(move |_| {
+        if modal_closing.get_untracked() {
+            return;
+        }
+        modal_closing.set(true);
+        spawn_local(async move {
+            TimeoutFuture::new(180).await;
+            modal_open.set(false);
+            modal_closing.set(false);
+        });
+    });
+
+    view! {
+        <button
+            class="app-header__icon-button app-header__icon-button--badged"
+            on:click=move |_| {
+                modal_closing.set(false);
+                modal_open.set(true);
+            }
+            title=move || match unread.get() {
+                0 => "Уведомления".to_string(),
+                n => format!("Уведомления: {} непрочитанных", n),
+            }
+        >
+            {icon("bell")}
+            <Show when=move || { unread.get() > 0 }>
+                <span class="app-header__badge">
+                    {move || if unread.get() > 99 { "99+".to_string() } else { unread.get().to_string() }}
+                </span>
+            </Show>
+        </button>
+        <Show when=move || modal_open.get()>
+            <NotificationsDrawer on_close=close_modal closing=modal_closing unread=unread />
+        </Show>
+    }
+}
+
+#[component]
+fn NotificationsDrawer(
+    on_close: Callback<()>,
+    closing: RwSignal<bool>,
+    unread: RwSignal<i64>,
+) -> impl IntoView {
+    let items = RwSignal::new(Vec::<NotificationDto>::new());
+    let loading = RwSignal::new(true);
+    let error = RwSignal::new(None::<String>);
+    let marking = RwSignal::new(false);
+    let tabs = use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match api::fetch_inbox().await {
+                Ok(inbox) => {
+                    unread.set(inbox.unread);
+                    items.set(inbox.items);
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    let mark_all_read = move |_| {
+        if marking.get_untracked() {
+            return;
+        }
+        marking.set(true);
+        spawn_local(async move {
+            match api::mark_all_read().await {
+                Ok(()) => {
+                    unread.set(0);
+                    let now = chrono::Utc::now().to_rfc3339();
+                    items.update(|list| {
+                        for item in list.iter_mut().filter(|i| i.read_at.is_none()) {
+                            item.read_at = Some(now.clone());
+                        }
+                    });
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            marking.set(false);
+        });
+    };
+
+    let open_exports = move |_| {
+        tabs.open_tab(EXPORTS_TAB_KEY, "Мои выгрузки");
+        on_close.run(());
+    };
+
+    let open_settings = move |_| {
+        tabs.open_tab(SETTINGS_TAB_KEY, "Уведомления");
+        on_close.run(());
+    };
+
+    view! {
+        <ModalFrame
+            on_close=on_close
+            overlay_style="align-items: stretch; justify-content: flex-end; padding: 0;".to_string()
+            overlay_class_signal=Signal::derive(move || {
+                if closing.get() {
+                    "favorite-drawer-overlay favorite-drawer-overlay--closing".to_string()
+                } else {
+                    "favorite-drawer-overlay".to_string()
+                }
+            })
+            modal_style="width: min(480px, 100vw); max-width: min(480px, 100vw); height: 100vh; max-height: 100vh; border-radius: 0; overflow: hidden;".to_string()
+            modal_class_signal=Signal::derive(move || {
+                if closing.get() {
+                    "favorite-modal favorite-modal--list favorite-drawer favorite-drawer--closing".to_string()
+                } else {
+                    "favorite-modal favorite-modal--list favorite-drawer".to_string()
+                }
+            })
+        >
+            <div class="favorite-modal__header">
+                <h3>"Уведомления"</h3>
+                <div style="display: flex; align-items: center; gap: 8px;">
+                    <button
+                        class="button button--secondary"
+                        on:click=mark_all_read
+                        disabled=move || marking.get() || unread.get() == 0
+                        title="Отметить все как прочитанные"
+                    >
+                        "Прочитать все"
+                    </button>
+                    <button class="button button--secondary" on:click=open_exports title="Мои выгрузки">
+                        {icon("download")}
+                    </button>
+                    <button class="button button--secondary" on:click=open_settings title="Настройки уведомлений">
+                        {icon("settings")}
+                    </button>
+                    <button class="favorite-modal__close" on:click=move |_| on_close.run(())>"x"</button>
+                </div>
+            </div>
+            <div class="favorite-modal__body">
+                <Show when=move || loading.get()>
+                    <div class="favorite-modal__loading">"Загрузка..."</div>
+                </Show>
+                <Show when=move || error.get().is_some()>
+                    <div class="favorite-modal__error">{move || error.get().unwrap_or_default()}</div>
+                </Show>
+                <Show when=move || !loading.get() && items.get().is_empty()>
+                    <div class="favorite-modal__empty">"Уведомлений нет"</div>
+                </Show>
+                <div class="windows-list__items">
+                    {move || {
+                        items
+                            .get()
+                            .into_iter()
+                            .map(|item| {
+                                let is_unread = item.read_at.is_none();
+                                let tab_key = item.tab_key.clone();
+                                let title = item.title.clone();
+                                let row_style = format!(
+                                    "display: flex; flex-direction: column; gap: 2px; width: 100%; \
+                                     padding: 6px 10px; text-align: left; background: transparent; \
+                                     border: none; border-top: 1px solid var(--color-border); \
+                                     cursor: {}; color: var(--color-text-primary); font-size: var(--font-size-sm); \
+                                     font-weight: {};",
+                                    if tab_key.is_some() { "pointer" } else { "default" },
+                                    if is_unread { "600" } else { "400" },
+                                );
+                                view! {
+                                    <button
+                                        class="windows-list__item"
+                                        style=row_style
+                                        on:click=move |_| {
+                                            if let Some(key) = tab_key.as_deref() {
+                                                tabs.open_tab(key, &title);
+                                                on_close.run(());
+                                            }
+                                        }
+                                    >
+                                        <span style="display: flex; gap: 8px; width: 100%;">
+                                            <span style="flex: 1 1 auto; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;">
+                                                {item.title.clone()}
+                                            </span>
+                                            <span style="flex: 0 0 auto; font-variant-numeric: tabular-nums; white-space: nowrap; color: var(--color-text-muted); font-weight: 400;">
+                                                {created_label(&item.created_at)}
+                                            </span>
+                                        </span>
+                                        <span style="color: var(--color-text-muted); font-weight: 400; white-space: pre-line; max-height: 4.5em; overflow: hidden;">
+                                            {item.body.clone()}
+                                        </span>
+                                    </button>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </div>
+            </div>
+        </ModalFrame>
+    }
+}
+
+/// Настройки уведомлений текущего пользователя: матрица «событие × канал».
+#[component]
+pub fn NotificationSettingsPage() -> impl IntoView {
+    let loading = RwSignal::new(false);
+    let saving = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let notice = RwSignal::<Option<String>>::new(None);
+    let settings = RwSignal::<Option<NotificationPreferencesDto>>::new(None);
+    let telegram_chat_id = RwSignal::new(String::new());
+
+    let apply = move |dto: NotificationPreferencesDto| {
+        telegram_chat_id.set(dto.telegram_chat_id.clone().unwrap_or_default());
+        settings.set(Some(dto));
+    };
+
+    let reload = Callback::new(move |_| {
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_preferences().await {
+                Ok(dto) => apply(dto),
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+    });
+
+    let toggle = move |event: NotificationEvent, channel: NotificationChannel, enabled: bool| {
+        settings.update(|s| {
+            let Some(s) = s else { return };
+            match s
+                .preferences
+                .iter_mut()
+                .find(|p| p.event == event && p.channel == channel)
+            {
+                Some(p) => p.enabled = enabled,
+                None => s.preferences.push(NotificationPreference {
+                    event,
+                    channel,
+                    enabled,
+                }),
+            }
+        });
+    };
+
+    let save = move |_| {
+        let Some(current) = settings.get_untracked() else {
+            return;
+        };
+        let chat_id = telegram_chat_id.get_untracked().trim().to_string();
+        let update = NotificationPreferencesUpdate {
+            preferences: current.preferences,
+            telegram_chat_id: (!chat_id.is_empty()).then_some(chat_id),
+        };
+        saving.set(true);
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::save_preferences(&update).await {
+                Ok(saved) => {
+                    apply(saved);
+                    notice.set(Some("Настройки уведомлений сохранены".to_string()));
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            saving.set(false);
+        });
+    };
+
+    let channel_hint = move |channel: NotificationChannel| -> Option<&'static str> {
+        let s = settings.get()?;
+        match channel {
+            NotificationChannel::InApp => None,
+            NotificationChannel::Email if !s.email_available => {
+                Some("отправка почты на сервере выключена")
+            }
+            NotificationChannel::Email if s.email.is_none() => Some("в профиле нет email"),
+            NotificationChannel::Telegram if !s.telegram_available => {
+                Some("бот Telegram на сервере не настроен")
+            }
+            NotificationChannel::Telegram if s.telegram_chat_id.is_none() => {
+                Some("укажите chat id ниже")
+            }
+            _ => None,
+        }
+    };
+
+    view! {
+        <PageFrame page_id="sys_notification_settings--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Уведомления"</h1>
+                    <p class="page__subtitle">"Выберите, о каких событиях и по каким каналам вас уведомлять. Уведомления «в приложении» появляются под колокольчиком в шапке."</p>
+                </div>
+                <div class="page__header-right">
+                    <button
+                        class="button button--secondary"
+                        disabled=move || loading.get()
+                        on:click=move |_| reload.run(())
+                    >
+                        {icon("refresh-cw")}
+                        {move || if loading.get() { "Обновление данных..." } else { "Обновить данные" }}
+                    </button>
+                    <button
+                        class="button button--primary"
+                        disabled=move || saving.get() || loading.get() || settings.get().is_none()
+                        on:click=save
+                    >
+                        {icon("save")} "Сохранить"
+                    </button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+                {move || notice.get().map(|msg| view! {
+                    <div class="alert alert--success">{msg}</div>
+                })}
+
+                <section class="raw-storage__section">
+                    <h2 class="raw-storage__section-title">"События и каналы"</h2>
+                    <div class="table-wrapper">
+                        <table class="table__data table--striped">
+                            <thead class="table__head">
+                                <tr>
+                                    <th class="table__header-cell">"Событие"</th>
+                                    {NotificationChannel::ALL
+                                        .into_iter()
+                                        .map(|channel| view! {
+                                            <th class="table__header-cell">
+                                                {channel.label()}
+                                                {move || channel_hint(channel).map(|hint| view! {
+                                                    <div class="page__subtitle">{hint}</div>
+                                                })}
+                                            </th>
+                                        })
+                                        .collect_view()}
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {NotificationEvent::ALL
+                                    .into_iter()
+                                    .map(|event| view! {
+                                        <tr class="table__row">
+                                            <td class="table__cell">{event.label()}</td>
+                                            {NotificationChannel::ALL
+                                                .into_iter()
+                                                .map(|channel| view! {
+                                                    <td class="table__cell">
+                                                        <input
+                                                            type="checkbox"
+                                                            disabled=move || settings.get().is_none()
+                                                            prop:checked=move || {
+                                                                settings
+                                                                    .get()
+                                                                    .is_some_and(|s| s.is_enabled(event, channel))
+                                                            }
+                                                            on:change=move |ev| {
+                                                                toggle(event, channel, event_target_checked(&ev))
+                                                            }
+                                                        />
+                                                    </td>
+                                                })
+                                                .collect_view()}
+                                        </tr>
+                                    })
+                                    .collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                </section>
+
+                <section class="raw-storage__section">
+                    <h2 class="raw-storage__section-title">"Адреса доставки"</h2>
+                    <div class="raw-storage__list">
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Email"</span>
+                            <span>
+                                {move || {
+                                    settings
+                                        .get()
+                                        .and_then(|s| s.email)
+                                        .unwrap_or_else(|| "— не указан в профиле".to_string())
+                                }}
+                            </span>
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Telegram chat id"</span>
+                            <Input value=telegram_chat_id placeholder="Например, 123456789 — напишите боту /start и узнайте свой id" />
+                        </div>
+                    </div>
+                </section>
+            </div>
+        </PageFrame>
+    }
+}
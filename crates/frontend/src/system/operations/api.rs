@@ -0,0 +1,125 @@
+use contracts::system::bulk_ops::BulkActionResultDto;
+use contracts::system::operations::{
+    OperationDto, OperationListResponse, OperationRequest, OperationStatus, StartOperationResponse,
+};
+use gloo_net::http::Request;
+use gloo_timers::future::TimeoutFuture;
+
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+
+/// Период опроса статуса запущенной операции.
+const POLL_INTERVAL_MS: u32 = 1_000;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+pub async fn start_operation(request: &OperationRequest) -> Result<String, String> {
+    let response = Request::post(&format!("{}/api/operations", api_base()))
+        .header("Authorization", &auth_header()?)
+        .json(request)
+        .map_err(|e| format!("Failed to serialize operation: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to start operation: {}", e))?;
+
+    if response.status() == 400 || response.status() == 403 {
+        let text = response.text().await.unwrap_or_default();
+        return Err(if text.is_empty() {
+            format!("Failed to start operation: HTTP {}", response.status())
+        } else {
+            text
+        });
+    }
+    if !response.ok() {
+        return Err(format!(
+            "Failed to start operation: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json::<StartOperationResponse>()
+        .await
+        .map(|r| r.operation_id)
+        .map_err(|e| format!("Failed to parse operation id: {}", e))
+}
+
+pub async fn fetch_operation(id: &str) -> Result<OperationDto, String> {
+    let response = Request::get(&format!("{}/api/operations/{}", api_base(), id))
+        .header("Authorization", &auth_header()?)
+        .header("Cache-Control", "no-cache")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch operation: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch operation: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse operation: {}", e))
+}
+
+pub async fn fetch_operations() -> Result<OperationListResponse, String> {
+    let response = Request::get(&format!("{}/api/operations", api_base()))
+        .header("Authorization", &auth_header()?)
+        .header("Cache-Control", "no-cache")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch operations: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch operations: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse operations: {}", e))
+}
+
+/// Запускает операцию и опрашивает её до завершения; `on_progress(processed, total)`
+/// вызывается после каждого опроса.
+pub async fn run_operation(
+    request: OperationRequest,
+    on_progress: impl Fn(usize, usize),
+) -> Result<BulkActionResultDto, String> {
+    let id = start_operation(&request).await?;
+    wait_operation(&id, request.item_count(), on_progress).await
+}
+
+/// Опрашивает уже запущенную операцию (например, пакет, поставленный в очередь
+/// эндпоинтом агрегата) до завершения.
+pub async fn wait_operation(
+    id: &str,
+    total: usize,
+    on_progress: impl Fn(usize, usize),
+) -> Result<BulkActionResultDto, String> {
+    on_progress(0, total);
+    loop {
+        TimeoutFuture::new(POLL_INTERVAL_MS).await;
+        let operation = fetch_operation(id).await?;
+        on_progress(operation.processed, operation.total);
+        match operation.status {
+            OperationStatus::Completed => return Ok(operation.result.unwrap_or_default()),
+            OperationStatus::Failed => {
+                return Err(operation
+                    .error
+                    .unwrap_or_else(|| "Операция завершилась с ошибкой".to_string()))
+            }
+            OperationStatus::Queued | OperationStatus::Running => {}
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use chrono::{DateTime, FixedOffset};
+use contracts::system::bulk_ops::BulkActionResultDto;
+use contracts::system::operations::{OperationDto, OperationRequest, OperationStatus};
+use gloo_timers::future::TimeoutFuture;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::shared::icons::icon;
+use crate::shared::modal_frame::ModalFrame;
+use crate::system::operations::api;
+
+/// Период опроса, пока есть незавершённые операции.
+const ACTIVE_POLL_INTERVAL_MS: u32 = 2_000;
+/// Период опроса в остальное время: операцию мог запустить другой экран.
+const IDLE_POLL_INTERVAL_MS: u32 = 30_000;
+
+/// Moscow timezone offset (UTC+3).
+const MSK_OFFSET_SECONDS: i32 = 3 * 3600;
+
+/// "DD.MM HH:MM" по Москве (исходная строка, если не разобрать).
+fn created_label(raw: &str) -> String {
+    FixedOffset::east_opt(MSK_OFFSET_SECONDS)
+        .and_then(|offset| {
+            DateTime::parse_from_rfc3339(raw)
+                .ok()
+                .map(|dt| dt.with_timezone(&offset).format("%d.%m %H:%M").to_string())
+        })
+        .unwrap_or_else(|| raw.to_string())
+}
+
+fn active_count(items: &[OperationDto]) -> usize {
+    items.iter().filter(|op| !op.status.is_finished()).count()
+}
+
+fn status_line(op: &OperationDto) -> String {
+    match (op.status, &op.result, &op.error) {
+        (OperationStatus::Completed, Some(result), _) if result.failed > 0 => format!(
+            "{}: успешно {}, с ошибкой {}",
+            op.status.label(),
+            result.succeeded,
+            result.failed
+        ),
+        (OperationStatus::Completed, Some(result), _) => {
+            format!("{}: успешно {}", op.status.label(), result.succeeded)
+        }
+        (OperationStatus::Failed, _, Some(error)) => format!("{}: {}", op.status.label(), error),
+        _ => format!("{}: {} из {}", op.status.label(), op.processed, op.total),
+    }
+}
+
+/// Выполняет пакетную операцию из списка документов: прогресс `(обработано, всего)`
+/// пишется в `progress`, ошибки показываются пользователю. Итог операции остаётся
+/// в панели «Операции».
+pub async fn run_documents_operation(
+    request: OperationRequest,
+    progress: WriteSignal<Option<(usize, usize)>>,
+) {
+    let outcome = api::run_operation(request, move |processed, total| {
+        progress.set(Some((processed, total)))
+    })
+    .await;
+    alert_outcome(outcome);
+}
+
+/// То же для операции, которую уже поставил в очередь эндпоинт агрегата.
+pub async fn track_documents_operation(
+    operation_id: String,
+    total: usize,
+    progress: WriteSignal<Option<(usize, usize)>>,
+) {
+    let outcome = api::wait_operation(&operation_id, total, move |processed, total| {
+        progress.set(Some((processed, total)))
+    })
+    .await;
+    alert_outcome(outcome);
+}
+
+fn alert_outcome(outcome: Result<BulkActionResultDto, String>) {
+    let message = match outcome {
+        Ok(result) if result.failed > 0 => Some(format!(
+            "Не обработано документов: {} из {}\n{}",
+            result.failed,
+            result.failed + result.succeeded,
+            result.errors.join("\n")
+        )),
+        Ok(_) => None,
+        Err(err) => Some(err),
+    };
+    if let (Some(message), Some(window)) = (message, web_sys::window()) {
+        let _ = window.alert_with_message(&message);
+    }
+}
+
+/// Кнопка «Операции» в шапке: счётчик выполняющихся пакетных операций
+/// и панель с прогрессом последних операций пользователя.
+#[component]
+pub fn OperationsHeaderButton() -> impl IntoView {
+    let items = RwSignal::new(Vec::<OperationDto>::new());
+    let loaded = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+    let modal_open = RwSignal::new(false);
+    let modal_closing = RwSignal::new(false);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            loop {
+                match api::fetch_operations().await {
+                    Ok(list) => {
+                        items.set(list.items);
+                        error.set(None);
+                    }
+                    Err(err) => error.set(Some(err)),
+                }
+                loaded.set(true);
+                // Панель открыта — прогресс должен двигаться и у только что запущенных операций.
+                let active = modal_open.get_untracked() || active_count(&items.get_untracked()) > 0;
+                TimeoutFuture::new(if active {
+                    ACTIVE_POLL_INTERVAL_MS
+                } else {
+                    IDLE_POLL_INTERVAL_MS
+                })
+                .await;
+            }
+        });
+    });
+
+    let running = Signal::derive(move || active_count(&items.get()));
+
+    let close_modal = Callback::new(move |_| {
+        if modal_closing.get_untracked() {
+            return;
+        }
+        modal_closing.set(true);
+        spawn_local(async move {
+            TimeoutFuture::new(180).await;
+            modal_open.set(false);
+            modal_closing.set(false);
+        });
+    });
+
+    view! {
+        <button
+            class="app-header__icon-button app-header__icon-button--badged"
+            on:click=move |_| {
+                modal_closing.set(false);
+                modal_open.set(true);
+            }
+            title=move || match running.get() {
+                0 => "Операции".to_string(),
+                n => format!("Операции: выполняется {}", n),
+            }
+        >
+            {icon("layers")}
+            <Show when=move || { running.get() > 0 }>
+                <span class="app-header__badge">{move || running.get().to_string()}</span>
+            </Show>
+        </button>
+        <Show when=move || modal_open.get()>
+            <OperationsDrawer
+                on_close=close_modal
+                closing=modal_closing
+                items=items
+                loaded=loaded
+                error=error
+            />
+        </Show>
+    }
+}
+
+#[component]
+fn OperationsDrawer(
+    on_close: Callback<()>,
+    closing: RwSignal<bool>,
+    items: RwSignal<Vec<OperationDto>>,
+    loaded: RwSignal<bool>,
+    error: RwSignal<Option<String>>,
+) -> impl IntoView {
+    view! {
+        <ModalFrame
+            on_close=on_close
+            overlay_style="align-items: stretch; justify-content: flex-end; padding: 0;".to_string()
+            overlay_class_signal=Signal::derive(move || {
+                if closing.get() {
+                    "favorite-drawer-overlay favorite-drawer-overlay--closing".to_string()
+                } else {
+                    "favorite-drawer-overlay".to_string()
+                }
+            })
+            modal_style="width: min(480px, 100vw); max-width: min(480px, 100vw); height: 100vh; max-height: 100vh; border-radius: 0; overflow: hidden;".to_string()
+            modal_class_signal=Signal::derive(move || {
+                if closing.get() {
+                    "favorite-modal favorite-modal--list favorite-drawer favorite-drawer--closing".to_string()
+                } else {
+                    "favorite-modal favorite-modal--list favorite-drawer".to_string()
+                }
+            })
+        >
+            <div class="favorite-modal__header">
+                <h3>"Операции"</h3>
+                <button class="favorite-modal__close" on:click=move |_| on_close.run(())>"x"</button>
+            </div>
+            <div class="favorite-modal__body">
+                <Show when=move || !loaded.get()>
+                    <div class="favorite-modal__loading">"Загрузка..."</div>
+                </Show>
+                <Show when=move || error.get().is_some()>
+                    <div class="favorite-modal__error">{move || error.get().unwrap_or_default()}</div>
+                </Show>
+                <Show when=move || loaded.get() && items.get().is_empty()>
+                    <div class="favorite-modal__empty">"Пакетных операций пока не было"</div>
+                </Show>
+                <div class="windows-list__items">
+                    {move || {
+                        items
+                            .get()
+                            .into_iter()
+                            .map(|op| {
+                                let percent = op.percent();
+                                let errors = op
+                                    .result
+                                    .as_ref()
+                                    .map(|r| r.errors.join("\n"))
+                                    .unwrap_or_default();
+                                view! {
+                                    <div
+                                        class="windows-list__item"
+                                        style="display: flex; flex-direction: column; gap: 4px; width: 100%; \
+                                               padding: 8px 10px; border-top: 1px solid var(--color-border); \
+                                               font-size: var(--font-size-sm); color: var(--color-text-primary);"
+                                    >
+                                        <span style="display: flex; gap: 8px; width: 100%;">
+                                            <span style="flex: 1 1 auto; overflow: hidden; text-overflow: ellipsis; white-space: nowrap; font-weight: 600;">
+                                                {op.title.clone()}
+                                            </span>
+                                            <span style="flex: 0 0 auto; font-variant-numeric: tabular-nums; white-space: nowrap; color: var(--color-text-muted);">
+                                                {created_label(&op.created_at)}
+                                            </span>
+                                        </span>
+                                        <div class="scheduled-task-details__progress-bar">
+                                            <div
+                                                class="scheduled-task-details__progress-bar-fill"
+                                                style=format!("width: {}%;", percent)
+                                            ></div>
+                                        </div>
+                                        <span class="scheduled-task-details__progress-meta" title=errors>
+                                            {status_line(&op)}
+                                        </span>
+                                    </div>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </div>
+            </div>
+        </ModalFrame>
+    }
+}
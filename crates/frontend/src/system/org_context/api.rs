@@ -0,0 +1,55 @@
+use contracts::system::org_context::{ActiveOrganizationDto, ActiveOrganizationUpdateRequest};
+use gloo_net::http::Request;
+
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+pub async fn get_active() -> Result<ActiveOrganizationDto, String> {
+    let response = Request::get(&format!("{}/api/system/active-organization", api_base()))
+        .header("Authorization", &auth_header()?)
+        .header("Cache-Control", "no-cache")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch active organization: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch active organization: {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json::<ActiveOrganizationDto>()
+        .await
+        .map_err(|e| format!("Failed to parse active organization: {}", e))
+}
+
+pub async fn set_active(organization_id: Option<String>) -> Result<ActiveOrganizationDto, String> {
+    let req = ActiveOrganizationUpdateRequest { organization_id };
+    let response = Request::put(&format!("{}/api/system/active-organization", api_base()))
+        .header("Authorization", &auth_header()?)
+        .json(&req)
+        .map_err(|e| format!("Failed to serialize active organization: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to set active organization: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to set active organization: {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json::<ActiveOrganizationDto>()
+        .await
+        .map_err(|e| format!("Failed to parse active organization: {}", e))
+}
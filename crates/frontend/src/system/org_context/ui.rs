@@ -0,0 +1,114 @@
+use contracts::system::org_context::ActiveOrganizationDto;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::shared::icons::icon;
+
+use super::api;
+
+const ALL_ORGANIZATIONS: &str = "Все организации";
+
+/// Переключатель активной организации в шапке. Списки и карточки документов
+/// показывают только данные выбранной организации; после смены страница
+/// перезагружается, открытые вкладки восстанавливаются из сессии вкладок.
+#[component]
+pub fn OrganizationSwitcher() -> impl IntoView {
+    let state = RwSignal::<Option<ActiveOrganizationDto>>::new(None);
+    let is_open = RwSignal::new(false);
+    let saving = RwSignal::new(false);
+
+    spawn_local(async move {
+        match api::get_active().await {
+            Ok(next) => state.set(Some(next)),
+            Err(err) => leptos::logging::warn!("{}", err),
+        }
+    });
+
+    let choose = move |organization_id: Option<String>| {
+        is_open.set(false);
+        let current = state.with_untracked(|s| s.as_ref().map(|s| s.organization_id.clone()));
+        if current == Some(organization_id.clone()) {
+            return;
+        }
+        saving.set(true);
+        spawn_local(async move {
+            match api::set_active(organization_id).await {
+                Ok(_) => {
+                    if let Some(window) = web_sys::window() {
+                        let _ = window.location().reload();
+                    }
+                }
+                Err(err) => {
+                    leptos::logging::warn!("{}", err);
+                    saving.set(false);
+                }
+            }
+        });
+    };
+
+    move || {
+        state.get().map(|dto| {
+            let label = dto
+                .organization_name
+                .clone()
+                .unwrap_or_else(|| ALL_ORGANIZATIONS.to_string());
+            if dto.locked_by_host {
+                return view! {
+                    <div
+                        class="app-header__user"
+                        style="cursor: default;"
+                        title="Организация определяется адресом сайта"
+                    >
+                        {icon("building")}
+                        <span>{label}</span>
+                    </div>
+                }
+                .into_any();
+            }
+
+            let active = dto.organization_id.clone();
+            let all = dto
+                .can_select_all
+                .then(|| (None, ALL_ORGANIZATIONS.to_string()));
+            let options: Vec<(Option<String>, String)> = all
+                .into_iter()
+                .chain(dto.organizations.into_iter().map(|o| (Some(o.id), o.name)))
+                .collect();
+
+            view! {
+                <div class="theme-select-wrapper">
+                    <button
+                        class="app-header__user"
+                        on:click=move |_| is_open.update(|v| *v = !*v)
+                        disabled=move || saving.get()
+                        title="Организация: списки и документы показываются только по ней"
+                    >
+                        {icon("building")}
+                        <span>{label}</span>
+                    </button>
+                    <Show when=move || is_open.get()>
+                        <div class="theme-dropdown">
+                            {options
+                                .clone()
+                                .into_iter()
+                                .map(|(id, name)| {
+                                    let class = if id == active {
+                                        "theme-dropdown__item theme-dropdown__item--active"
+                                    } else {
+                                        "theme-dropdown__item"
+                                    };
+                                    view! {
+                                        <button class=class on:click=move |_| choose(id.clone())>
+                                            {name}
+                                        </button>
+                                    }
+                                })
+                                .collect_view()}
+                        </div>
+                    </Show>
+                </div>
+            }
+            .into_any()
+        })
+    }
+}
@@ -1,8 +1,29 @@
+use contracts::system::auth::{LoginResponse, OidcLoginInfo};
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 
 use crate::shared::theme::ThemeSelect;
 use crate::system::auth::{api, context::use_auth, storage};
+use crate::system::branding::context::use_branding;
+
+/// Параметры, с которыми OIDC callback возвращает браузер на фронтенд.
+/// Читаются один раз и убираются из адресной строки.
+fn take_oidc_params() -> (Option<String>, Option<String>) {
+    let Some(window) = web_sys::window() else {
+        return (None, None);
+    };
+    let search = window.location().search().unwrap_or_default();
+    let params: std::collections::HashMap<String, String> =
+        serde_qs::from_str(search.trim_start_matches('?')).unwrap_or_default();
+    let code = params.get("oidc_code").cloned();
+    let error = params.get("oidc_error").cloned();
+    if code.is_some() || error.is_some() {
+        if let (Ok(history), Ok(path)) = (window.history(), window.location().pathname()) {
+            let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&path));
+        }
+    }
+    (code, error)
+}
 
 #[component]
 pub fn LoginPage() -> impl IntoView {
@@ -12,6 +33,50 @@ pub fn LoginPage() -> impl IntoView {
     let is_loading = RwSignal::new(false);
 
     let (_, set_auth_state) = use_auth();
+    let branding = use_branding();
+    let product_name = move || branding.with(|b| b.product_name.clone());
+    let oidc_info = RwSignal::new(OidcLoginInfo::default());
+
+    let complete_login = move |response: LoginResponse| {
+        // Save tokens
+        storage::save_access_token(&response.access_token);
+        storage::save_refresh_token(&response.refresh_token);
+
+        // Update auth state - это автоматически переключит на MainLayout
+        set_auth_state.set(crate::system::auth::context::AuthState {
+            access_token: Some(response.access_token),
+            user_info: Some(response.user),
+            preview: None,
+        });
+    };
+
+    // Возврат после входа через OIDC: меняем одноразовый код на токены
+    let (oidc_code, oidc_error) = take_oidc_params();
+    if let Some(err) = oidc_error {
+        error_message.set(Some(format!("Ошибка входа через SSO: {}", err)));
+    }
+    if let Some(code) = oidc_code {
+        is_loading.set(true);
+        spawn_local(async move {
+            match api::exchange_oidc_code(code).await {
+                Ok(response) => complete_login(response),
+                Err(e) => error_message.set(Some(format!("Ошибка входа через SSO: {}", e))),
+            }
+            is_loading.set(false);
+        });
+    }
+    spawn_local(async move {
+        if let Ok(info) = api::fetch_oidc_info().await {
+            oidc_info.set(info);
+        }
+    });
+
+    let start_oidc = move |_| {
+        is_loading.set(true);
+        if let Some(window) = web_sys::window() {
+            let _ = window.location().set_href(&api::oidc_start_url());
+        }
+    };
 
     let on_submit = move |ev: leptos::ev::SubmitEvent| {
         ev.prevent_default();
@@ -25,16 +90,7 @@ pub fn LoginPage() -> impl IntoView {
         spawn_local(async move {
             match api::login(username_val, password_val).await {
                 Ok(response) => {
-                    // Save tokens
-                    storage::save_access_token(&response.access_token);
-                    storage::save_refresh_token(&response.refresh_token);
-
-                    // Update auth state - это автоматически переключит на MainLayout
-                    set_auth_state.set(crate::system::auth::context::AuthState {
-                        access_token: Some(response.access_token),
-                        user_info: Some(response.user),
-                    });
-
+                    complete_login(response);
                     is_loading.set(false);
                 }
                 Err(e) => {
@@ -56,9 +112,18 @@ pub fn LoginPage() -> impl IntoView {
             <div class="login__card">
                 <div class="login__header">
                     <div class="login__logo">
-                        <img src="assets/images/gears.svg" alt="Integrator" width="64" height="64" />
+                        <img
+                            src=move || {
+                                branding
+                                    .with(|b| b.logo_url.clone())
+                                    .unwrap_or_else(|| "assets/images/gears.svg".to_string())
+                            }
+                            alt=product_name
+                            width="64"
+                            height="64"
+                        />
                     </div>
-                    <h1 class="login__title">"Integrator"</h1>
+                    <h1 class="login__title">{product_name}</h1>
                     <p class="login__subtitle">"Войдите в систему"</p>
                 </div>
 
@@ -135,6 +200,19 @@ pub fn LoginPage() -> impl IntoView {
                 </div>
             </form>
 
+                <Show when=move || oidc_info.with(|i| i.enabled)>
+                    <div class="login__footer">
+                        <button
+                            type="button"
+                            class="button button--secondary login__button"
+                            disabled=move || is_loading.get()
+                            on:click=start_oidc
+                        >
+                            {move || format!("Войти: {}", oidc_info.with(|i| i.display_name.clone()))}
+                        </button>
+                    </div>
+                </Show>
+
 
             </div>
         </div>
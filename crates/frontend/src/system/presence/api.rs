@@ -0,0 +1,66 @@
+use contracts::system::presence::{PresenceSavedRequest, PresenceUpdateRequest};
+use gloo_net::http::Request;
+
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+/// URL SSE-потока присутствия; токен передаётся в query, т.к. EventSource
+/// не поддерживает заголовки.
+pub fn stream_url(entity: &str, entity_id: &str) -> Option<String> {
+    let token = storage::get_access_token()?;
+    Some(format!(
+        "{}/api/system/presence/stream?entity={}&entity_id={}&token={}",
+        api_base(),
+        urlencoding::encode(entity),
+        urlencoding::encode(entity_id),
+        urlencoding::encode(&token)
+    ))
+}
+
+pub async fn set_editing(entity: &str, entity_id: &str, editing: bool) -> Result<(), String> {
+    let request = PresenceUpdateRequest {
+        entity: entity.to_string(),
+        entity_id: entity_id.to_string(),
+        editing,
+    };
+    let response = Request::post(&format!("{}/api/system/presence", api_base()))
+        .header("Authorization", &auth_header()?)
+        .json(&request)
+        .map_err(|e| format!("Failed to serialize presence: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to update presence: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to update presence: HTTP {}",
+            response.status()
+        ));
+    }
+    Ok(())
+}
+
+pub async fn notify_saved(entity: &str, entity_id: &str) -> Result<(), String> {
+    let request = PresenceSavedRequest {
+        entity: entity.to_string(),
+        entity_id: entity_id.to_string(),
+    };
+    let response = Request::post(&format!("{}/api/system/presence/saved", api_base()))
+        .header("Authorization", &auth_header()?)
+        .json(&request)
+        .map_err(|e| format!("Failed to serialize presence: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to report save: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!("Failed to report save: HTTP {}", response.status()));
+    }
+    Ok(())
+}
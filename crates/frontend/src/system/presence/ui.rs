@@ -0,0 +1,216 @@
+//! Присутствие на формах документов: подписка на SSE-поток по открытой записи,
+//! полоса аватаров коллег и предупреждение перед сохранением поверх чужих изменений.
+
+use contracts::system::presence::{initials, PresenceSavedDto, PresenceSnapshot, PresenceUserDto};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{EventSource, MessageEvent};
+
+use crate::system::auth::context::use_auth;
+use crate::system::presence::api;
+
+/// Открытый EventSource; закрывается при drop (смена записи или размонтирование формы).
+struct PresenceConnection {
+    source: EventSource,
+    _on_presence: Closure<dyn FnMut(MessageEvent)>,
+}
+
+impl Drop for PresenceConnection {
+    fn drop(&mut self) {
+        self.source.close();
+    }
+}
+
+fn connect(
+    entity: &str,
+    entity_id: &str,
+    snapshot: RwSignal<Option<PresenceSnapshot>>,
+) -> Option<PresenceConnection> {
+    let source = EventSource::new(&api::stream_url(entity, entity_id)?).ok()?;
+    let on_presence = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+        if let Some(data) = event.data().as_string() {
+            if let Ok(value) = serde_json::from_str::<PresenceSnapshot>(&data) {
+                snapshot.set(Some(value));
+            }
+        }
+    });
+    source
+        .add_event_listener_with_callback("presence", on_presence.as_ref().unchecked_ref())
+        .ok()?;
+    Some(PresenceConnection {
+        source,
+        _on_presence: on_presence,
+    })
+}
+
+/// Сохранение другим пользователем после того, как `user_id` открыл запись.
+fn saved_by_other(snapshot: &PresenceSnapshot, user_id: &str) -> Option<PresenceSavedDto> {
+    let since = &snapshot.users.iter().find(|u| u.user_id == user_id)?.since;
+    snapshot.saved_by_other_since(user_id, since).cloned()
+}
+
+/// Присутствие текущего пользователя на форме записи.
+#[derive(Clone, Copy)]
+pub struct PresenceHandle {
+    entity: &'static str,
+    entity_id: Signal<Option<String>>,
+    user_id: StoredValue<String>,
+    snapshot: RwSignal<Option<PresenceSnapshot>>,
+    editing_sent: RwSignal<bool>,
+}
+
+/// Подключает форму к потоку присутствия. Пока `entity_id` пуст (новая запись),
+/// соединения нет; при смене id переподключается.
+pub fn use_presence(entity: &'static str, entity_id: Signal<Option<String>>) -> PresenceHandle {
+    let (auth_state, _) = use_auth();
+    let user_id = StoredValue::new(
+        auth_state
+            .get_untracked()
+            .user_info
+            .map(|u| u.id)
+            .unwrap_or_default(),
+    );
+    let snapshot = RwSignal::new(None::<PresenceSnapshot>);
+    let editing_sent = RwSignal::new(false);
+    // Local storage: EventSource и Closure не Send; значение освобождается вместе с формой
+    let connection = StoredValue::new_local(None::<PresenceConnection>);
+
+    Effect::new(move |_| {
+        let id = entity_id.get();
+        connection.set_value(None);
+        snapshot.set(None);
+        editing_sent.set(false);
+        if let Some(id) = id {
+            connection.set_value(connect(entity, &id, snapshot));
+        }
+    });
+
+    PresenceHandle {
+        entity,
+        entity_id,
+        user_id,
+        snapshot,
+        editing_sent,
+    }
+}
+
+impl PresenceHandle {
+    /// Другие пользователи, открывшие запись.
+    pub fn others(&self) -> Vec<PresenceUserDto> {
+        self.snapshot
+            .get()
+            .map(|s| s.others(&self.user_id.get_value()))
+            .unwrap_or_default()
+    }
+
+    /// Запись сохранил другой пользователь после того, как мы её открыли.
+    pub fn saved_by_other(&self) -> Option<PresenceSavedDto> {
+        let user_id = self.user_id.get_value();
+        self.snapshot
+            .with(|s| saved_by_other(s.as_ref()?, &user_id))
+    }
+
+    /// Сообщить остальным, есть ли у нас несохранённые изменения.
+    pub fn set_editing(&self, editing: bool) {
+        let Some(id) = self.entity_id.get_untracked() else {
+            return;
+        };
+        if self.editing_sent.get_untracked() == editing {
+            return;
+        }
+        self.editing_sent.set(editing);
+        let entity = self.entity;
+        spawn_local(async move {
+            let _ = api::set_editing(entity, &id, editing).await;
+        });
+    }
+
+    /// Подтверждение перед сохранением, если запись правит или уже сохранил кто-то ещё.
+    pub fn confirm_save(&self) -> bool {
+        let user_id = self.user_id.get_value();
+        let editors: Vec<String> = self
+            .snapshot
+            .get_untracked()
+            .map(|s| s.others(&user_id))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|u| u.editing)
+            .map(|u| u.username)
+            .collect();
+        let saved = self
+            .snapshot
+            .with_untracked(|s| saved_by_other(s.as_ref()?, &user_id));
+        let mut reasons = Vec::new();
+        if let Some(saved) = saved {
+            reasons.push(format!(
+                "{} уже сохранил(а) эту запись после того, как вы её открыли.",
+                saved.username
+            ));
+        }
+        if !editors.is_empty() {
+            reasons.push(format!(
+                "Сейчас запись редактирует: {}.",
+                editors.join(", ")
+            ));
+        }
+        if reasons.is_empty() {
+            return true;
+        }
+        let message = format!(
+            "{}\nСохранить ваши изменения? Чужие правки могут быть перезаписаны.",
+            reasons.join("\n")
+        );
+        web_sys::window()
+            .and_then(|w| w.confirm_with_message(&message).ok())
+            .unwrap_or(false)
+    }
+
+    /// Запись сохранена: снять отметку редактирования и предупредить остальных.
+    pub fn notify_saved(&self) {
+        let Some(id) = self.entity_id.get_untracked() else {
+            return;
+        };
+        self.editing_sent.set(false);
+        let entity = self.entity;
+        spawn_local(async move {
+            let _ = api::notify_saved(entity, &id).await;
+        });
+    }
+}
+
+/// Полоса аватаров пользователей, открывших ту же запись.
+#[component]
+pub fn PresenceStrip(presence: PresenceHandle) -> impl IntoView {
+    view! {
+        <Show when=move || !presence.others().is_empty() || presence.saved_by_other().is_some()>
+            <div class="presence-strip">
+                {move || {
+                    presence
+                        .others()
+                        .into_iter()
+                        .map(|user| {
+                            let title = if user.editing {
+                                format!("{} — редактирует", user.username)
+                            } else {
+                                format!("{} — просматривает", user.username)
+                            };
+                            let class = if user.editing {
+                                "presence-strip__avatar presence-strip__avatar--editing"
+                            } else {
+                                "presence-strip__avatar"
+                            };
+                            view! { <span class=class title=title>{initials(&user.username)}</span> }
+                        })
+                        .collect_view()
+                }}
+                {move || presence.saved_by_other().map(|saved| view! {
+                    <span class="presence-strip__warning">
+                        {format!("{} сохранил(а) запись — перезагрузите форму, чтобы увидеть изменения", saved.username)}
+                    </span>
+                })}
+            </div>
+        </Show>
+    }
+}
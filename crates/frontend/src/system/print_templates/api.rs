@@ -0,0 +1,149 @@
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+use contracts::system::print_templates::{
+    PrintTemplateDto, PrintTemplateUpsertRequest, RenderedPrintDocumentDto,
+};
+use gloo_net::http::{Request, Response};
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+async fn saved_template(response: Response) -> Result<PrintTemplateDto, String> {
+    if response.status() == 400 {
+        return Err(
+            "Название или шаблон пустые, слишком длинные или содержат неизвестные поля".to_string(),
+        );
+    }
+    if !response.ok() {
+        return Err(format!(
+            "Failed to save template: HTTP {}",
+            response.status()
+        ));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse template: {}", e))
+}
+
+/// Все шаблоны (страница администратора).
+pub async fn fetch_all() -> Result<Vec<PrintTemplateDto>, String> {
+    let response = Request::get(&format!("{}/api/sys/print-templates", api_base()))
+        .header("Authorization", &auth_header()?)
+        .header("Cache-Control", "no-cache")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch templates: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch templates: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse templates: {}", e))
+}
+
+pub async fn create(req: &PrintTemplateUpsertRequest) -> Result<PrintTemplateDto, String> {
+    let response = Request::post(&format!("{}/api/sys/print-templates", api_base()))
+        .header("Authorization", &auth_header()?)
+        .json(req)
+        .map_err(|e| format!("Failed to serialize template: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to save template: {}", e))?;
+    saved_template(response).await
+}
+
+pub async fn update(
+    id: &str,
+    req: &PrintTemplateUpsertRequest,
+) -> Result<PrintTemplateDto, String> {
+    let response = Request::put(&format!("{}/api/sys/print-templates/{}", api_base(), id))
+        .header("Authorization", &auth_header()?)
+        .json(req)
+        .map_err(|e| format!("Failed to serialize template: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to save template: {}", e))?;
+    saved_template(response).await
+}
+
+pub async fn delete(id: &str) -> Result<(), String> {
+    let response = Request::delete(&format!("{}/api/sys/print-templates/{}", api_base(), id))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to delete template: {}", e))?;
+
+    if !response.ok() && response.status() != 404 {
+        return Err(format!(
+            "Failed to delete template: HTTP {}",
+            response.status()
+        ));
+    }
+    Ok(())
+}
+
+/// Шаблоны агрегата для карточки документа.
+pub async fn fetch_for_entity(entity_type: &str) -> Result<Vec<PrintTemplateDto>, String> {
+    let url = format!(
+        "{}/api/system/print-templates?entity_type={}",
+        api_base(),
+        urlencoding::encode(entity_type)
+    );
+    let response = Request::get(&url)
+        .header("Authorization", &auth_header()?)
+        .header("Cache-Control", "no-cache")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch print templates: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch print templates: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse print templates: {}", e))
+}
+
+pub async fn render(
+    template_id: &str,
+    document_id: &str,
+) -> Result<RenderedPrintDocumentDto, String> {
+    let url = format!(
+        "{}/api/system/print-templates/{}/render/{}",
+        api_base(),
+        template_id,
+        document_id
+    );
+    let response = Request::get(&url)
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to render document: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to render document: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse rendered document: {}", e))
+}
@@ -0,0 +1,364 @@
+mod print_panel;
+
+pub use print_panel::PrintDocumentPanel;
+
+use contracts::system::print_templates::{
+    print_template_entity, render_print_template, sample_values, starter_body_html,
+    unknown_placeholders, PrintTemplateDto, PrintTemplateKind, PrintTemplateUpsertRequest,
+    PRINT_TEMPLATE_ENTITIES,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::shared::date_utils::format_datetime_utc_local;
+use crate::shared::icons::icon;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::auth::guard::RequireAdmin;
+use crate::system::print_templates::api;
+
+fn entity_label(entity_type: &str) -> &str {
+    print_template_entity(entity_type)
+        .map(|e| e.label)
+        .unwrap_or(entity_type)
+}
+
+#[component]
+pub fn PrintTemplatesPage() -> impl IntoView {
+    view! {
+        <RequireAdmin>
+            <PrintTemplatesContent />
+        </RequireAdmin>
+    }
+}
+
+/// Открытый в редакторе шаблон; `id = None` — новый.
+#[derive(Clone, Copy)]
+struct Draft {
+    id: RwSignal<Option<String>>,
+    entity_type: RwSignal<String>,
+    kind: RwSignal<PrintTemplateKind>,
+    name: RwSignal<String>,
+    body_html: RwSignal<String>,
+}
+
+#[component]
+fn PrintTemplatesContent() -> impl IntoView {
+    let items = RwSignal::<Vec<PrintTemplateDto>>::new(Vec::new());
+    let loading = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let editing = RwSignal::new(false);
+    let draft = Draft {
+        id: RwSignal::new(None),
+        entity_type: RwSignal::new(PRINT_TEMPLATE_ENTITIES[0].entity_type.to_string()),
+        kind: RwSignal::new(PrintTemplateKind::ClaimLetter),
+        name: RwSignal::new(String::new()),
+        body_html: RwSignal::new(String::new()),
+    };
+
+    let reload = Callback::new(move |_| {
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_all().await {
+                Ok(next) => items.set(next),
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+    });
+
+    let start_new = move |_| {
+        draft.id.set(None);
+        draft.kind.set(PrintTemplateKind::ClaimLetter);
+        draft
+            .name
+            .set(PrintTemplateKind::ClaimLetter.label().to_string());
+        draft
+            .body_html
+            .set(starter_body_html(PrintTemplateKind::ClaimLetter));
+        editing.set(true);
+    };
+
+    let start_edit = move |item: PrintTemplateDto| {
+        draft.id.set(Some(item.id));
+        draft.entity_type.set(item.entity_type);
+        draft.kind.set(item.kind);
+        draft.name.set(item.name);
+        draft.body_html.set(item.body_html);
+        editing.set(true);
+    };
+
+    let remove = move |item: PrintTemplateDto| {
+        let msg = format!("Удалить шаблон «{}»?", item.name);
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message(&msg).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        spawn_local(async move {
+            match api::delete(&item.id).await {
+                Ok(()) => reload.run(()),
+                Err(err) => error.set(Some(err)),
+            }
+        });
+    };
+
+    view! {
+        <PageFrame page_id="sys_print_templates--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Печатные формы"</h1>
+                    <p class="page__subtitle">"HTML-шаблоны претензионных писем и актов с полями вида {{supplier_article}}. Заполняются данными документа на вкладке «Связи» и печатаются в PDF из браузера."</p>
+                </div>
+                <div class="page__header-right">
+                    <button class="button button--primary" on:click=start_new>
+                        {icon("plus")} "Новый шаблон"
+                    </button>
+                    <button
+                        class="button button--secondary"
+                        disabled=move || loading.get()
+                        on:click=move |_| reload.run(())
+                    >
+                        {icon("refresh-cw")}
+                        {move || if loading.get() { "Обновление данных..." } else { "Обновить данные" }}
+                    </button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+
+                <div class="table-wrapper">
+                    <table class="table__data table--striped">
+                        <thead class="table__head">
+                            <tr>
+                                <th class="table__header-cell">"Документ"</th>
+                                <th class="table__header-cell">"Вид"</th>
+                                <th class="table__header-cell">"Название"</th>
+                                <th class="table__header-cell">"Изменён"</th>
+                                <th class="table__header-cell"></th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {move || {
+                                let list = items.get();
+                                if list.is_empty() {
+                                    let text = if loading.get() { "Загрузка..." } else { "Шаблонов нет" };
+                                    return view! {
+                                        <tr class="table__row">
+                                            <td class="table__cell" colspan="5">{text}</td>
+                                        </tr>
+                                    }
+                                    .into_any();
+                                }
+                                list.into_iter()
+                                    .map(|item| {
+                                        let for_edit = item.clone();
+                                        let for_remove = item.clone();
+                                        view! {
+                                            <tr class="table__row">
+                                                <td class="table__cell">{entity_label(&item.entity_type).to_string()}</td>
+                                                <td class="table__cell">{item.kind.label()}</td>
+                                                <td class="table__cell">{item.name.clone()}</td>
+                                                <td class="table__cell" style="white-space: nowrap;">
+                                                    {format!(
+                                                        "{} · {}",
+                                                        format_datetime_utc_local(&item.updated_at, "%d.%m.%Y %H:%M"),
+                                                        item.updated_by
+                                                    )}
+                                                </td>
+                                                <td class="table__cell" style="white-space: nowrap;">
+                                                    <button
+                                                        class="button button--ghost button--small"
+                                                        on:click=move |_| start_edit(for_edit.clone())
+                                                    >
+                                                        {icon("edit")}
+                                                    </button>
+                                                    <button
+                                                        class="button button--ghost button--small"
+                                                        on:click=move |_| remove(for_remove.clone())
+                                                    >
+                                                        {icon("trash-2")}
+                                                    </button>
+                                                </td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()
+                                    .into_any()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+
+                <Show when=move || editing.get()>
+                    <TemplateEditor draft=draft on_done=Callback::new(move |saved: bool| {
+                        editing.set(false);
+                        if saved {
+                            reload.run(());
+                        }
+                    }) />
+                </Show>
+            </div>
+        </PageFrame>
+    }
+}
+
+#[component]
+fn TemplateEditor(draft: Draft, on_done: Callback<bool>) -> impl IntoView {
+    let busy = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+
+    let entity = move || {
+        print_template_entity(&draft.entity_type.get()).unwrap_or(&PRINT_TEMPLATE_ENTITIES[0])
+    };
+    let unknown = move || unknown_placeholders(entity(), &draft.body_html.get());
+    let preview = move || render_print_template(&draft.body_html.get(), &sample_values(entity()));
+
+    let save = move |_| {
+        let id = draft.id.get_untracked();
+        let req = PrintTemplateUpsertRequest {
+            entity_type: draft.entity_type.get_untracked(),
+            kind: draft.kind.get_untracked(),
+            name: draft.name.get_untracked(),
+            body_html: draft.body_html.get_untracked(),
+        };
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            let result = match id {
+                Some(id) => api::update(&id, &req).await,
+                None => api::create(&req).await,
+            };
+            busy.set(false);
+            match result {
+                Ok(_) => on_done.run(true),
+                Err(err) => error.set(Some(err)),
+            }
+        });
+    };
+
+    let chips = move || {
+        entity()
+            .placeholders
+            .iter()
+            .map(|(name, label, _)| {
+                let token = format!("{{{{{}}}}}", name);
+                let text = token.clone();
+                view! {
+                    <button
+                        class="button button--ghost button--small"
+                        title=label.to_string()
+                        on:click=move |_| draft.body_html.update(|body| body.push_str(&token))
+                    >
+                        {text}
+                    </button>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <section class="raw-storage__section">
+            <h2 class="raw-storage__section-title">
+                {move || if draft.id.get().is_some() { "Изменение шаблона" } else { "Новый шаблон" }}
+            </h2>
+            {move || error.get().map(|err| view! {
+                <div class="alert alert--error">{err}</div>
+            })}
+            <div class="raw-storage__list">
+                <div class="raw-storage__list-row">
+                    <span class="raw-storage__list-label">"Документ"</span>
+                    <select
+                        class="form__select"
+                        prop:value=move || draft.entity_type.get()
+                        on:change=move |ev| draft.entity_type.set(event_target_value(&ev))
+                    >
+                        {PRINT_TEMPLATE_ENTITIES
+                            .iter()
+                            .map(|e| view! { <option value=e.entity_type>{e.label}</option> })
+                            .collect_view()}
+                    </select>
+                </div>
+                <div class="raw-storage__list-row">
+                    <span class="raw-storage__list-label">"Вид"</span>
+                    <select
+                        class="form__select"
+                        prop:value=move || draft.kind.get().code()
+                        on:change=move |ev| {
+                            if let Some(kind) = PrintTemplateKind::from_code(&event_target_value(&ev)) {
+                                draft.kind.set(kind);
+                            }
+                        }
+                    >
+                        {PrintTemplateKind::all()
+                            .into_iter()
+                            .map(|k| view! { <option value=k.code()>{k.label()}</option> })
+                            .collect_view()}
+                    </select>
+                </div>
+                <div class="raw-storage__list-row">
+                    <span class="raw-storage__list-label">"Название"</span>
+                    <input
+                        class="form__input"
+                        type="text"
+                        prop:value=move || draft.name.get()
+                        on:input=move |ev| draft.name.set(event_target_value(&ev))
+                    />
+                </div>
+                <div class="raw-storage__list-row">
+                    <span class="raw-storage__list-label">"Поля"</span>
+                    <div class="raw-storage__list-action-group">{chips}</div>
+                </div>
+                <div class="print-templates__editor">
+                    <textarea
+                        class="form__textarea print-templates__body"
+                        prop:value=move || draft.body_html.get()
+                        on:input=move |ev| draft.body_html.set(event_target_value(&ev))
+                    ></textarea>
+                    <iframe
+                        class="print-templates__preview"
+                        title="Предпросмотр"
+                        sandbox=""
+                        srcdoc=preview
+                    ></iframe>
+                </div>
+                {move || {
+                    let unknown = unknown();
+                    (!unknown.is_empty()).then(|| view! {
+                        <div class="alert alert--error">
+                            {format!("Неизвестные поля: {}", unknown.join(", "))}
+                        </div>
+                    })
+                }}
+                <div class="raw-storage__list-row">
+                    <span class="raw-storage__list-label">"Предпросмотр — на примерах значений"</span>
+                    <div class="raw-storage__list-action-group">
+                        <button
+                            class="button button--primary"
+                            disabled=move || busy.get() || !unknown().is_empty()
+                            on:click=save
+                        >
+                            {icon("check")} "Сохранить"
+                        </button>
+                        <button
+                            class="button button--secondary"
+                            disabled=move || busy.get()
+                            on:click=move |_| on_done.run(false)
+                        >
+                            {icon("x")} "Отмена"
+                        </button>
+                    </div>
+                </div>
+            </div>
+        </section>
+    }
+}
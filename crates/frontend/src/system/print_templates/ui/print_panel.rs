@@ -0,0 +1,115 @@
+use contracts::system::print_templates::{escape_html, PrintTemplateDto, RenderedPrintDocumentDto};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use wasm_bindgen::JsValue;
+use web_sys::{window, BlobPropertyBag, Url};
+
+use crate::shared::components::card_animated::CardAnimated;
+use crate::shared::icons::icon;
+use crate::system::print_templates::api;
+
+/// Открывает заполненную форму в новом окне и сразу вызывает печать — оттуда
+/// «Сохранить как PDF».
+fn open_print_window(doc: &RenderedPrintDocumentDto) {
+    let Some(win) = window() else { return };
+    let html = format!(
+        r#"<!DOCTYPE html><html><head><meta charset="utf-8">
+<title>{title}</title>
+<style>@page{{size:A4;margin:20mm;}}</style>
+</head><body>
+{body}
+<script>window.onload=()=>window.print();</script></body></html>"#,
+        title = escape_html(&doc.title),
+        body = doc.html,
+    );
+
+    let arr = js_sys::Array::new();
+    arr.push(&JsValue::from_str(&html));
+    let opts = BlobPropertyBag::new();
+    opts.set_type("text/html;charset=utf-8");
+    if let Ok(blob) = web_sys::Blob::new_with_str_sequence_and_options(&arr, &opts) {
+        if let Ok(url) = Url::create_object_url_with_blob(&blob) {
+            let _ = win.open_with_url_and_target(&url, "_blank");
+        }
+    }
+}
+
+/// Печатные формы документа (претензии, акты) на вкладке «Связи»: по кнопке форма
+/// заполняется данными документа на сервере и открывается на печать.
+#[component]
+pub fn PrintDocumentPanel(
+    /// Агрегат документа (`a012_wb_sales`, `a015_wb_orders`)
+    entity_type: &'static str,
+    /// id документа; пока `None`, печатать нечего
+    #[prop(into)]
+    document_id: Signal<Option<String>>,
+) -> impl IntoView {
+    let templates = RwSignal::<Vec<PrintTemplateDto>>::new(Vec::new());
+    let busy = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match api::fetch_for_entity(entity_type).await {
+                Ok(list) => templates.set(list),
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    });
+
+    let print = move |template_id: String| {
+        let Some(document_id) = document_id.get_untracked() else {
+            return;
+        };
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::render(&template_id, &document_id).await {
+                Ok(doc) => open_print_window(&doc),
+                Err(e) => error.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    view! {
+        <CardAnimated delay_ms=0 nav_id="document_print_forms">
+            <h4 class="details-section__title">"Печатные формы"</h4>
+
+            {move || error.get().map(|e| view! { <div class="alert alert--error">{e}</div> })}
+
+            {move || {
+                let list = templates.get();
+                if list.is_empty() {
+                    return view! {
+                        <div style="color: var(--color-text-secondary);">
+                            "Шаблоны не настроены — их добавляет администратор в разделе «Печатные формы»."
+                        </div>
+                    }
+                    .into_any();
+                }
+                view! {
+                    <div class="raw-storage__list-action-group">
+                        {list
+                            .into_iter()
+                            .map(|template| {
+                                let id = template.id.clone();
+                                view! {
+                                    <button
+                                        class="button button--secondary"
+                                        title=template.kind.label()
+                                        disabled=move || busy.get() || document_id.get().is_none()
+                                        on:click=move |_| print(id.clone())
+                                    >
+                                        {icon("file-text")} {template.name.clone()}
+                                    </button>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                }
+                .into_any()
+            }}
+        </CardAnimated>
+    }
+}
@@ -0,0 +1,58 @@
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+use contracts::system::projection_archive::{
+    ArchiveMoveRequest, ArchiveMoveResultDto, ArchiveStatusDto,
+};
+use gloo_net::http::Request;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+pub async fn fetch_status() -> Result<ArchiveStatusDto, String> {
+    let response = Request::get(&format!("{}/api/sys/projection-archive/status", api_base()))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch archive status: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch archive status: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse archive status: {}", e))
+}
+
+pub async fn move_to_archive(before_month: String) -> Result<ArchiveMoveResultDto, String> {
+    let req = ArchiveMoveRequest { before_month };
+    let response = Request::post(&format!("{}/api/sys/projection-archive/move", api_base()))
+        .header("Authorization", &auth_header()?)
+        .json(&req)
+        .map_err(|e| format!("Failed to serialize archive request: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to move to archive: {}", e))?;
+
+    if response.status() == 400 {
+        return Err("Месяц должен быть в формате ГГГГ-ММ".to_string());
+    }
+    if !response.ok() {
+        return Err(format!(
+            "Failed to move to archive: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse archive result: {}", e))
+}
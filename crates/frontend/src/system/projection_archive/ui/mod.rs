@@ -0,0 +1,185 @@
+use contracts::system::projection_archive::{ArchiveStatusDto, ArchiveTableDto};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::Input;
+
+use crate::shared::icons::icon;
+use crate::shared::money_format::format_number;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::auth::guard::RequireAdmin;
+use crate::system::projection_archive::api;
+
+#[component]
+pub fn ProjectionArchivePage() -> impl IntoView {
+    view! {
+        <RequireAdmin>
+            <ProjectionArchiveContent />
+        </RequireAdmin>
+    }
+}
+
+#[component]
+fn ProjectionArchiveContent() -> impl IntoView {
+    let status = RwSignal::<Option<ArchiveStatusDto>>::new(None);
+    let loading = RwSignal::new(false);
+    let action_busy = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let notice = RwSignal::<Option<String>>::new(None);
+    let month_input = RwSignal::new(String::new());
+
+    let reload = Callback::new(move |_| {
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_status().await {
+                Ok(next) => {
+                    if month_input.get_untracked().is_empty() {
+                        month_input.set(next.boundary.clone().unwrap_or_default());
+                    }
+                    status.set(Some(next));
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+    });
+
+    let run_move = move |_| {
+        let month = month_input.get_untracked().trim().to_string();
+        let msg = format!(
+            "Перенести в архив все строки p900/p904 раньше {}? Отчёты за эти месяцы продолжат работать, но будут читать и архив.",
+            month
+        );
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message(&msg).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+
+        action_busy.set(true);
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::move_to_archive(month).await {
+                Ok(result) => {
+                    let moved: u64 = result.tables.iter().map(|t| t.moved_rows).sum();
+                    notice.set(Some(format!(
+                        "Граница архива: {}, перенесено строк: {}",
+                        result.boundary,
+                        format_number(moved as f64, 0)
+                    )));
+                    reload.run(());
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            action_busy.set(false);
+        });
+    };
+
+    view! {
+        <PageFrame page_id="sys_projection_archive--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Архив проекций"</h1>
+                    <p class="page__subtitle">"Строки p900/p904 старше границы хранятся в архивных таблицах. Запросы за месяцы после границы читают только рабочие таблицы."</p>
+                </div>
+                <div class="page__header-right">
+                    <button
+                        class="button button--secondary"
+                        disabled=move || loading.get()
+                        on:click=move |_| reload.run(())
+                    >
+                        {icon("refresh-cw")}
+                        {move || if loading.get() { "Обновление данных..." } else { "Обновить данные" }}
+                    </button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+                {move || notice.get().map(|msg| view! {
+                    <div class="alert alert--success">{msg}</div>
+                })}
+
+                <section class="raw-storage__section">
+                    <div class="raw-storage__list">
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Текущая граница архива"</span>
+                            <span class="raw-storage__list-value">
+                                {move || status.get()
+                                    .and_then(|s| s.boundary)
+                                    .unwrap_or_else(|| "архивация не выполнялась".to_string())}
+                            </span>
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Перенести в архив строки раньше месяца (ГГГГ-ММ)"</span>
+                            <div class="raw-storage__list-action-group">
+                                <Input class="raw-storage__days-input" value=month_input placeholder="2024-01" />
+                                <button class="button button--secondary" disabled=move || action_busy.get() on:click=run_move>
+                                    {icon("layers")} "Перенести"
+                                </button>
+                            </div>
+                        </div>
+                    </div>
+                </section>
+
+                {move || {
+                    status.get()
+                        .map(|s| s.tables)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|table| view! { <ArchiveTableSection table=table /> })
+                        .collect_view()
+                }}
+            </div>
+        </PageFrame>
+    }
+}
+
+#[component]
+fn ArchiveTableSection(table: ArchiveTableDto) -> impl IntoView {
+    view! {
+        <section class="raw-storage__section">
+            <h2 class="raw-storage__section-title">
+                {format!(
+                    "{}: рабочая {} / архив {}",
+                    table.table,
+                    format_number(table.hot_rows as f64, 0),
+                    format_number(table.archive_rows as f64, 0)
+                )}
+            </h2>
+            <div class="table-wrapper">
+                <table class="table__data table--striped">
+                    <thead class="table__head">
+                        <tr>
+                            <th class="table__header-cell">"Месяц"</th>
+                            <th class="table__header-cell" style="text-align: right;">"Рабочая таблица"</th>
+                            <th class="table__header-cell" style="text-align: right;">"Архив"</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {table
+                            .partitions
+                            .into_iter()
+                            .map(|p| view! {
+                                <tr class="table__row">
+                                    <td class="table__cell">{p.month}</td>
+                                    <td class="table__cell" style="text-align: right;">{format_number(p.hot_rows as f64, 0)}</td>
+                                    <td class="table__cell" style="text-align: right;">{format_number(p.archive_rows as f64, 0)}</td>
+                                </tr>
+                            })
+                            .collect_view()}
+                    </tbody>
+                </table>
+            </div>
+        </section>
+    }
+}
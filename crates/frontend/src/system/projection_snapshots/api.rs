@@ -0,0 +1,104 @@
+use contracts::system::projection_snapshots::{
+    CreateProjectionSnapshotRequest, ProjectionSnapshotDto, SnapshotCompareResponse,
+};
+use gloo_net::http::{Request, Response};
+
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+/// Текст ошибки сервера (400 с пояснением) или HTTP-статус.
+async fn error_text(response: Response, action: &str) -> String {
+    let status = response.status();
+    match response.text().await {
+        Ok(text) if status == 400 && !text.is_empty() => text,
+        _ => format!("{}: HTTP {}", action, status),
+    }
+}
+
+pub async fn list_snapshots() -> Result<Vec<ProjectionSnapshotDto>, String> {
+    let response = Request::get(&format!("{}/api/sys/projection-snapshots", api_base()))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch snapshots: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "Failed to fetch snapshots").await);
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse snapshots: {}", e))
+}
+
+pub async fn create_snapshot(
+    req: &CreateProjectionSnapshotRequest,
+) -> Result<ProjectionSnapshotDto, String> {
+    let response = Request::post(&format!("{}/api/sys/projection-snapshots", api_base()))
+        .header("Authorization", &auth_header()?)
+        .json(req)
+        .map_err(|e| format!("Failed to serialize snapshot request: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to create snapshot: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "Failed to create snapshot").await);
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse snapshot: {}", e))
+}
+
+pub async fn delete_snapshot(id: &str) -> Result<(), String> {
+    let response = Request::delete(&format!(
+        "{}/api/sys/projection-snapshots/{}",
+        api_base(),
+        urlencoding::encode(id)
+    ))
+    .header("Authorization", &auth_header()?)
+    .send()
+    .await
+    .map_err(|e| format!("Failed to delete snapshot: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "Failed to delete snapshot").await);
+    }
+    Ok(())
+}
+
+pub async fn compare_snapshots(
+    base: &str,
+    target: &str,
+    only_changed: bool,
+) -> Result<SnapshotCompareResponse, String> {
+    let response = Request::get(&format!(
+        "{}/api/sys/projection-snapshots/compare?base={}&target={}&only_changed={}",
+        api_base(),
+        urlencoding::encode(base),
+        urlencoding::encode(target),
+        only_changed
+    ))
+    .header("Authorization", &auth_header()?)
+    .send()
+    .await
+    .map_err(|e| format!("Failed to compare snapshots: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "Failed to compare snapshots").await);
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse comparison: {}", e))
+}
@@ -0,0 +1,484 @@
+//! «Снимки проекций»: фиксация итогов p900 по месяцам и SKU и сравнение снимков
+//! между собой или с текущими данными — почему выручка отчитанного месяца изменилась.
+
+pub mod api;
+
+use chrono::Utc;
+use contracts::system::projection_snapshots::{
+    CreateProjectionSnapshotRequest, ProjectionSnapshotDto, SnapshotCompareResponse,
+    SnapshotDiffRowDto, SnapshotDiffStatus, SNAPSHOT_CURRENT,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::{Button, ButtonAppearance};
+
+use crate::shared::date_utils::format_utc_local;
+use crate::shared::icons::icon;
+use crate::shared::money_format::{format_money, format_number};
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::auth::guard::RequireAdmin;
+
+const INPUT_STYLE: &str =
+    "padding:6px 10px;border:1px solid var(--color-border);border-radius:4px;font-size:0.85em;";
+
+fn status_background(status: SnapshotDiffStatus) -> &'static str {
+    match status {
+        SnapshotDiffStatus::Added => "var(--colorPaletteGreenBackground1,#ecfdf5)",
+        SnapshotDiffStatus::Removed => "var(--colorPaletteRedBackground1,#fef2f2)",
+        SnapshotDiffStatus::Changed => "var(--colorPaletteYellowBackground1,#fffbeb)",
+        SnapshotDiffStatus::Same => "transparent",
+    }
+}
+
+fn delta_color(delta: f64) -> &'static str {
+    if delta > 0.0 {
+        "var(--color-success,#16a34a)"
+    } else if delta < 0.0 {
+        "var(--color-error,#dc2626)"
+    } else {
+        "var(--color-text-secondary)"
+    }
+}
+
+fn format_delta(delta: f64) -> String {
+    if delta > 0.0 {
+        format!("+{}", format_money(delta))
+    } else {
+        format_money(delta)
+    }
+}
+
+fn format_created(raw: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(raw)
+        .map(|dt| format_utc_local(&dt.with_timezone(&Utc), "%d.%m.%Y %H:%M"))
+        .unwrap_or_else(|_| raw.to_string())
+}
+
+/// Предыдущий месяц `YYYY-MM` — период по умолчанию для нового снимка.
+fn previous_month() -> String {
+    let today = Utc::now().date_naive();
+    today
+        .checked_sub_months(chrono::Months::new(1))
+        .unwrap_or(today)
+        .format("%Y-%m")
+        .to_string()
+}
+
+#[component]
+pub fn ProjectionSnapshotsPage() -> impl IntoView {
+    view! {
+        <RequireAdmin>
+            <ProjectionSnapshotsContent />
+        </RequireAdmin>
+    }
+}
+
+#[component]
+fn ProjectionSnapshotsContent() -> impl IntoView {
+    let snapshots = RwSignal::<Vec<ProjectionSnapshotDto>>::new(Vec::new());
+    let loading = RwSignal::new(false);
+    let busy = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let notice = RwSignal::<Option<String>>::new(None);
+
+    let label = RwSignal::new(String::new());
+    let period_from = RwSignal::new(previous_month());
+    let period_to = RwSignal::new(previous_month());
+
+    let base = RwSignal::new(String::new());
+    let target = RwSignal::new(SNAPSHOT_CURRENT.to_string());
+    let only_changed = RwSignal::new(true);
+    let comparison = RwSignal::<Option<SnapshotCompareResponse>>::new(None);
+
+    let reload = Callback::new(move |_| {
+        loading.set(true);
+        spawn_local(async move {
+            match api::list_snapshots().await {
+                Ok(list) => {
+                    if base.get_untracked().is_empty() {
+                        if let Some(first) = list.first() {
+                            base.set(first.id.clone());
+                        }
+                    }
+                    snapshots.set(list);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+    });
+
+    let run_compare = Callback::new(move |_| {
+        let base_id = base.get_untracked();
+        if base_id.is_empty() {
+            error.set(Some("Выберите базовый снимок".to_string()));
+            return;
+        }
+        let target_id = target.get_untracked();
+        let changed = only_changed.get_untracked();
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::compare_snapshots(&base_id, &target_id, changed).await {
+                Ok(result) => comparison.set(Some(result)),
+                Err(e) => error.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    });
+
+    let create = move |_| {
+        let req = CreateProjectionSnapshotRequest {
+            label: label.get_untracked().trim().to_string(),
+            period_from: period_from.get_untracked().trim().to_string(),
+            period_to: period_to.get_untracked().trim().to_string(),
+        };
+        busy.set(true);
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::create_snapshot(&req).await {
+                Ok(snapshot) => {
+                    notice.set(Some(format!(
+                        "Снимок «{}» сохранён: {} строк, выручка {}",
+                        snapshot.label,
+                        format_number(snapshot.row_count as f64, 0),
+                        format_money(snapshot.revenue)
+                    )));
+                    label.set(String::new());
+                    reload.run(());
+                }
+                Err(e) => error.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let remove = Callback::new(move |snapshot: ProjectionSnapshotDto| {
+        let msg = format!("Удалить снимок «{}»?", snapshot.label);
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message(&msg).ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        spawn_local(async move {
+            match api::delete_snapshot(&snapshot.id).await {
+                Ok(()) => {
+                    if base.get_untracked() == snapshot.id {
+                        base.set(String::new());
+                    }
+                    if target.get_untracked() == snapshot.id {
+                        target.set(SNAPSHOT_CURRENT.to_string());
+                    }
+                    reload.run(());
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    });
+
+    view! {
+        <PageFrame page_id="sys_projection_snapshots--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Снимки проекций"</h1>
+                    <p class="page__subtitle">"Итоги p900 по месяцам и SKU на момент снимка. Сравнение показывает, что изменилось в уже отчитанном периоде."</p>
+                </div>
+                <div class="page__header-right">
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on_click=move |_| reload.run(())
+                        disabled=Signal::derive(move || loading.get())
+                    >
+                        {icon("refresh")}
+                        " Обновить"
+                    </Button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|e| view! { <div class="alert alert--error">{e}</div> })}
+                {move || notice.get().map(|m| view! { <div class="alert alert--success">{m}</div> })}
+
+                <div style="display:flex;gap:8px;align-items:center;flex-wrap:wrap;margin-bottom:12px;">
+                    <input
+                        type="text"
+                        placeholder="Название (например, «Отчёт за сентябрь»)"
+                        style=format!("{}min-width:280px;", INPUT_STYLE)
+                        prop:value=move || label.get()
+                        on:input=move |e| label.set(event_target_value(&e))
+                    />
+                    <input
+                        type="month"
+                        style=INPUT_STYLE
+                        prop:value=move || period_from.get()
+                        on:change=move |e| period_from.set(event_target_value(&e))
+                    />
+                    "—"
+                    <input
+                        type="month"
+                        style=INPUT_STYLE
+                        prop:value=move || period_to.get()
+                        on:change=move |e| period_to.set(event_target_value(&e))
+                    />
+                    <Button
+                        appearance=ButtonAppearance::Primary
+                        on_click=create
+                        disabled=Signal::derive(move || busy.get())
+                    >
+                        {icon("save")}
+                        " Зафиксировать итоги"
+                    </Button>
+                </div>
+
+                <table style="border-collapse:collapse;font-size:0.85em;width:100%;margin-bottom:16px;">
+                    <thead>
+                        <tr style="text-align:left;border-bottom:1px solid var(--color-border);">
+                            <th style="padding:6px 8px;">"Снимок"</th>
+                            <th style="padding:6px 8px;">"Период"</th>
+                            <th style="padding:6px 8px;text-align:right;">"Строк"</th>
+                            <th style="padding:6px 8px;text-align:right;">"Выручка"</th>
+                            <th style="padding:6px 8px;">"Создан"</th>
+                            <th style="padding:6px 8px;"></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {move || {
+                            snapshots
+                                .get()
+                                .into_iter()
+                                .map(|s| {
+                                    let compare_id = s.id.clone();
+                                    let removed = s.clone();
+                                    view! {
+                                        <tr style="border-bottom:1px solid var(--color-border);">
+                                            <td style="padding:4px 8px;">{s.label.clone()}</td>
+                                            <td style="padding:4px 8px;white-space:nowrap;">
+                                                {format!("{} — {}", s.period_from, s.period_to)}
+                                            </td>
+                                            <td style="padding:4px 8px;text-align:right;">
+                                                {format_number(s.row_count as f64, 0)}
+                                            </td>
+                                            <td style="padding:4px 8px;text-align:right;">{format_money(s.revenue)}</td>
+                                            <td style="padding:4px 8px;white-space:nowrap;">
+                                                {format!(
+                                                    "{} {}",
+                                                    format_created(&s.created_at),
+                                                    s.created_by.clone().unwrap_or_default()
+                                                )}
+                                            </td>
+                                            <td style="padding:4px 8px;white-space:nowrap;text-align:right;">
+                                                <button
+                                                    class="button button--secondary"
+                                                    on:click=move |_| {
+                                                        base.set(compare_id.clone());
+                                                        target.set(SNAPSHOT_CURRENT.to_string());
+                                                        run_compare.run(());
+                                                    }
+                                                >
+                                                    {icon("columns")}
+                                                    " С текущими"
+                                                </button>
+                                                <button
+                                                    class="button button--ghost"
+                                                    title="Удалить снимок"
+                                                    on:click=move |_| remove.run(removed.clone())
+                                                >
+                                                    {icon("trash-2")}
+                                                </button>
+                                            </td>
+                                        </tr>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </tbody>
+                </table>
+
+                <div style="display:flex;gap:8px;align-items:center;flex-wrap:wrap;margin-bottom:12px;">
+                    <span>"Было:"</span>
+                    <select style=INPUT_STYLE on:change=move |e| base.set(event_target_value(&e))>
+                        {move || {
+                            snapshots
+                                .get()
+                                .into_iter()
+                                .map(|s| {
+                                    let id = s.id.clone();
+                                    view! {
+                                        <option value=s.id.clone() selected=move || base.get() == id>
+                                            {s.label.clone()}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </select>
+                    <span>"Стало:"</span>
+                    <select style=INPUT_STYLE on:change=move |e| target.set(event_target_value(&e))>
+                        <option value=SNAPSHOT_CURRENT selected=move || target.get() == SNAPSHOT_CURRENT>
+                            "Текущие данные"
+                        </option>
+                        {move || {
+                            snapshots
+                                .get()
+                                .into_iter()
+                                .map(|s| {
+                                    let id = s.id.clone();
+                                    view! {
+                                        <option value=s.id.clone() selected=move || target.get() == id>
+                                            {s.label.clone()}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </select>
+                    <label style="display:flex;align-items:center;gap:6px;font-size:0.85em;cursor:pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || only_changed.get()
+                            on:change=move |e| only_changed.set(event_target_checked(&e))
+                        />
+                        "Только изменения"
+                    </label>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on_click=move |_| run_compare.run(())
+                        disabled=Signal::derive(move || busy.get())
+                    >
+                        {icon("columns")}
+                        " Сравнить"
+                    </Button>
+                </div>
+
+                {move || comparison.get().map(|c| view! { <ComparisonView comparison=c /> })}
+            </div>
+        </PageFrame>
+    }
+}
+
+#[component]
+fn ComparisonView(comparison: SnapshotCompareResponse) -> impl IntoView {
+    let summary = format!(
+        "«{}» → «{}», {} — {}: изменено строк {}{}",
+        comparison.base_label,
+        comparison.target_label,
+        comparison.period_from,
+        comparison.period_to,
+        comparison.changed_rows,
+        if comparison.truncated {
+            " (показаны крупнейшие)"
+        } else {
+            ""
+        }
+    );
+
+    view! {
+        <div style="font-size:0.85em;color:var(--color-text-secondary);margin-bottom:8px;">{summary}</div>
+
+        <table style="border-collapse:collapse;font-size:0.85em;margin-bottom:16px;">
+            <thead>
+                <tr style="text-align:left;border-bottom:1px solid var(--color-border);">
+                    <th style="padding:6px 8px;">"Месяц"</th>
+                    <th style="padding:6px 8px;text-align:right;">"Выручка (было)"</th>
+                    <th style="padding:6px 8px;text-align:right;">"Выручка (стало)"</th>
+                    <th style="padding:6px 8px;text-align:right;">"Разница"</th>
+                    <th style="padding:6px 8px;text-align:right;">"Изменено строк"</th>
+                </tr>
+            </thead>
+            <tbody>
+                {comparison
+                    .periods
+                    .into_iter()
+                    .map(|p| {
+                        let delta = p.target_revenue - p.base_revenue;
+                        view! {
+                            <tr style="border-bottom:1px solid var(--color-border);">
+                                <td style="padding:4px 8px;">{p.period}</td>
+                                <td style="padding:4px 8px;text-align:right;">{format_money(p.base_revenue)}</td>
+                                <td style="padding:4px 8px;text-align:right;">{format_money(p.target_revenue)}</td>
+                                <td style=format!("padding:4px 8px;text-align:right;font-weight:600;color:{};", delta_color(delta))>
+                                    {format_delta(delta)}
+                                </td>
+                                <td style="padding:4px 8px;text-align:right;">{p.changed_rows}</td>
+                            </tr>
+                        }
+                    })
+                    .collect_view()}
+            </tbody>
+        </table>
+
+        <div style="overflow-x:auto;">
+            <table style="border-collapse:collapse;font-size:0.82em;width:100%;min-width:900px;">
+                <thead>
+                    <tr style="text-align:left;border-bottom:1px solid var(--color-border);">
+                        <th style="padding:6px 8px;">"Месяц"</th>
+                        <th style="padding:6px 8px;">"МП"</th>
+                        <th style="padding:6px 8px;">"SKU"</th>
+                        <th style="padding:6px 8px;">"Статус"</th>
+                        <th style="padding:6px 8px;text-align:right;">"Кол-во"</th>
+                        <th style="padding:6px 8px;text-align:right;">"Выручка (было)"</th>
+                        <th style="padding:6px 8px;text-align:right;">"Выручка (стало)"</th>
+                        <th style="padding:6px 8px;text-align:right;">"Разница"</th>
+                        <th style="padding:6px 8px;text-align:right;">"Себестоимость"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {comparison
+                        .rows
+                        .into_iter()
+                        .map(|row| view! { <DiffRow row=row /> })
+                        .collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[component]
+fn DiffRow(row: SnapshotDiffRowDto) -> impl IntoView {
+    let delta = row.target_revenue - row.base_revenue;
+    let qty = if row.base_qty == row.target_qty {
+        format_number(row.target_qty, 0)
+    } else {
+        format!(
+            "{} → {}",
+            format_number(row.base_qty, 0),
+            format_number(row.target_qty, 0)
+        )
+    };
+    let cost = if row.base_cost == row.target_cost {
+        format_money(row.target_cost)
+    } else {
+        format!(
+            "{} → {}",
+            format_money(row.base_cost),
+            format_money(row.target_cost)
+        )
+    };
+
+    view! {
+        <tr style=format!(
+            "border-bottom:1px solid var(--color-border);background:{};",
+            status_background(row.status),
+        )>
+            <td style="padding:4px 8px;white-space:nowrap;">{row.period.clone()}</td>
+            <td style="padding:4px 8px;">{row.marketplace.clone()}</td>
+            <td style="padding:4px 8px;font-family:monospace;">{row.sku.clone()}</td>
+            <td style="padding:4px 8px;">{row.status.label()}</td>
+            <td style="padding:4px 8px;text-align:right;white-space:nowrap;">{qty}</td>
+            <td style="padding:4px 8px;text-align:right;">{format_money(row.base_revenue)}</td>
+            <td style="padding:4px 8px;text-align:right;">{format_money(row.target_revenue)}</td>
+            <td style=format!("padding:4px 8px;text-align:right;font-weight:600;color:{};", delta_color(delta))>
+                {format_delta(delta)}
+            </td>
+            <td style="padding:4px 8px;text-align:right;white-space:nowrap;">{cost}</td>
+        </tr>
+    }
+}
@@ -0,0 +1,53 @@
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+use contracts::system::quotas::{OrgQuotaDto, OrgQuotaUpdateRequest};
+use gloo_net::http::Request;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+pub async fn fetch_all() -> Result<Vec<OrgQuotaDto>, String> {
+    let response = Request::get(&format!("{}/api/sys/quotas", api_base()))
+        .header("Authorization", &auth_header()?)
+        .header("Cache-Control", "no-cache")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch quotas: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch quotas: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse quotas: {}", e))
+}
+
+pub async fn save(organization_id: &str, req: OrgQuotaUpdateRequest) -> Result<(), String> {
+    let response = Request::put(&format!(
+        "{}/api/sys/quotas/{}",
+        api_base(),
+        urlencoding::encode(organization_id)
+    ))
+    .header("Authorization", &auth_header()?)
+    .json(&req)
+    .map_err(|e| format!("Failed to serialize quota: {}", e))?
+    .send()
+    .await
+    .map_err(|e| format!("Failed to save quota: {}", e))?;
+
+    if response.status() == 400 {
+        return Err("Лимиты должны быть неотрицательными числами".to_string());
+    }
+    if !response.ok() {
+        return Err(format!("Failed to save quota: HTTP {}", response.status()));
+    }
+    Ok(())
+}
@@ -0,0 +1,263 @@
+use contracts::system::quotas::{OrgQuotaDto, OrgQuotaUpdateRequest};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::shared::date_utils::format_datetime_utc_local;
+use crate::shared::icons::icon;
+use crate::shared::money_format::format_number;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::auth::guard::RequireAdmin;
+use crate::system::quotas::api;
+
+/// Пустое поле — без ограничения.
+fn parse_limit(value: &str) -> Result<Option<i64>, String> {
+    let value = value.trim().replace(' ', "");
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value
+        .parse::<i64>()
+        .ok()
+        .filter(|limit| *limit >= 0)
+        .map(Some)
+        .ok_or_else(|| format!("Некорректный лимит: {}", value))
+}
+
+/// «использовано / лимит» и цвет по доле выбранного лимита.
+fn usage_cell(used: i64, limit: Option<i64>) -> impl IntoView {
+    let text = match limit {
+        Some(limit) => format!(
+            "{} / {}",
+            format_number(used as f64, 0),
+            format_number(limit as f64, 0)
+        ),
+        None => format!("{} / без лимита", format_number(used as f64, 0)),
+    };
+    let color = match OrgQuotaDto::usage_ratio(used, limit) {
+        Some(ratio) if ratio >= 1.0 => "color: var(--color-error); font-weight: 600;",
+        Some(ratio) if ratio >= 0.8 => "color: var(--color-warning);",
+        _ => "",
+    };
+    view! { <td class="table__cell table__cell--right" style=color>{text}</td> }
+}
+
+#[component]
+pub fn QuotasPage() -> impl IntoView {
+    view! {
+        <RequireAdmin>
+            <QuotasContent />
+        </RequireAdmin>
+    }
+}
+
+#[component]
+fn QuotasContent() -> impl IntoView {
+    let items = RwSignal::<Vec<OrgQuotaDto>>::new(Vec::new());
+    let loading = RwSignal::new(false);
+    let saving = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let notice = RwSignal::<Option<String>>::new(None);
+
+    let editing = RwSignal::<Option<OrgQuotaDto>>::new(None);
+    let requests_per_day = RwSignal::new(String::new());
+    let export_rows_per_month = RwSignal::new(String::new());
+
+    let reload = Callback::new(move |_| {
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_all().await {
+                Ok(next) => items.set(next),
+                Err(err) => error.set(Some(err)),
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+    });
+
+    let start_edit = move |item: OrgQuotaDto| {
+        requests_per_day.set(
+            item.requests_per_day
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+        );
+        export_rows_per_month.set(
+            item.export_rows_per_month
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+        );
+        notice.set(None);
+        editing.set(Some(item));
+    };
+
+    let save = move |_| {
+        let Some(item) = editing.get_untracked() else {
+            return;
+        };
+        let req = match (
+            parse_limit(&requests_per_day.get_untracked()),
+            parse_limit(&export_rows_per_month.get_untracked()),
+        ) {
+            (Ok(requests_per_day), Ok(export_rows_per_month)) => OrgQuotaUpdateRequest {
+                requests_per_day,
+                export_rows_per_month,
+            },
+            (Err(err), _) | (_, Err(err)) => {
+                error.set(Some(err));
+                return;
+            }
+        };
+        saving.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::save(&item.organization_id, req).await {
+                Ok(()) => {
+                    notice.set(Some(format!(
+                        "Квоты «{}» сохранены",
+                        item.organization_name
+                    )));
+                    editing.set(None);
+                    reload.run(());
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            saving.set(false);
+        });
+    };
+
+    view! {
+        <PageFrame page_id="sys_quotas--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Квоты организаций"</h1>
+                    <p class="page__subtitle">"Лимиты запросов к API в сутки и строк выгрузок в месяц (UTC). Организация запроса определяется по хосту из брендирования; сверх лимита сервер отвечает 429."</p>
+                </div>
+                <div class="page__header-right">
+                    <button
+                        class="button button--secondary"
+                        disabled=move || loading.get()
+                        on:click=move |_| reload.run(())
+                    >
+                        {icon("refresh-cw")}
+                        {move || if loading.get() { "Обновление данных..." } else { "Обновить данные" }}
+                    </button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+                {move || notice.get().map(|msg| view! {
+                    <div class="alert alert--success">{msg}</div>
+                })}
+
+                {move || editing.get().map(|item| view! {
+                    <section class="raw-storage__section">
+                        <h2 class="raw-storage__section-title">{item.organization_name.clone()}</h2>
+                        <div class="raw-storage__list">
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label">"Запросов к API в сутки"</span>
+                                <input
+                                    class="form__input"
+                                    type="number"
+                                    min="0"
+                                    placeholder="без лимита"
+                                    prop:value=move || requests_per_day.get()
+                                    on:input=move |ev| requests_per_day.set(event_target_value(&ev))
+                                />
+                            </div>
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label">"Строк выгрузок в месяц"</span>
+                                <input
+                                    class="form__input"
+                                    type="number"
+                                    min="0"
+                                    placeholder="без лимита"
+                                    prop:value=move || export_rows_per_month.get()
+                                    on:input=move |ev| export_rows_per_month.set(event_target_value(&ev))
+                                />
+                            </div>
+                            <div class="raw-storage__list-row">
+                                <span class="raw-storage__list-label"></span>
+                                <div class="raw-storage__list-action-group">
+                                    <button class="button button--primary" disabled=move || saving.get() on:click=save>
+                                        {icon("check")} "Сохранить"
+                                    </button>
+                                    <button class="button button--secondary" on:click=move |_| editing.set(None)>
+                                        "Отмена"
+                                    </button>
+                                </div>
+                            </div>
+                        </div>
+                    </section>
+                })}
+
+                <section class="raw-storage__section">
+                    <div class="table-wrapper">
+                        <table class="table__data table--striped">
+                            <thead class="table__head">
+                                <tr>
+                                    <th class="table__header-cell">"Организация"</th>
+                                    <th class="table__header-cell">"Хосты"</th>
+                                    <th class="table__header-cell">"Запросов сегодня"</th>
+                                    <th class="table__header-cell">"Строк выгрузок за месяц"</th>
+                                    <th class="table__header-cell">"Изменено"</th>
+                                    <th class="table__header-cell"></th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {move || {
+                                    items
+                                        .get()
+                                        .into_iter()
+                                        .map(|item| {
+                                            let for_edit = item.clone();
+                                            let hosts = if item.hosts.is_empty() {
+                                                "— (не определяется по хосту)".to_string()
+                                            } else {
+                                                item.hosts.join(", ")
+                                            };
+                                            let changed = item
+                                                .updated_at
+                                                .as_deref()
+                                                .map(|at| {
+                                                    format!(
+                                                        "{} · {}",
+                                                        format_datetime_utc_local(at, "%d.%m.%Y %H:%M"),
+                                                        item.updated_by.clone().unwrap_or_default()
+                                                    )
+                                                })
+                                                .unwrap_or_default();
+                                            view! {
+                                                <tr class="table__row">
+                                                    <td class="table__cell">{item.organization_name.clone()}</td>
+                                                    <td class="table__cell">{hosts}</td>
+                                                    {usage_cell(item.requests_today, item.requests_per_day)}
+                                                    {usage_cell(item.export_rows_month, item.export_rows_per_month)}
+                                                    <td class="table__cell">{changed}</td>
+                                                    <td class="table__cell">
+                                                        <button
+                                                            class="button button--secondary"
+                                                            on:click=move |_| start_edit(for_edit.clone())
+                                                        >
+                                                            {icon("edit")} "Лимиты"
+                                                        </button>
+                                                    </td>
+                                                </tr>
+                                            }
+                                        })
+                                        .collect_view()
+                                }}
+                            </tbody>
+                        </table>
+                    </div>
+                </section>
+            </div>
+        </PageFrame>
+    }
+}
@@ -1,5 +1,7 @@
 use crate::shared::api_utils::api_base;
 use crate::system::auth::storage;
+use crate::system::environment::confirm::confirm_danger;
+use contracts::system::environment::CONFIRM_TOKEN_HEADER;
 use contracts::system::raw_storage::{
     DbVacuumResult, DbVacuumStatus, DbWalCheckpointResult, RawStorageCleanupPreview,
     RawStorageCleanupRequest, RawStorageSettings, RawStorageStatus,
@@ -83,8 +85,14 @@ pub async fn cleanup_preview(
 }
 
 pub async fn cleanup(req: &RawStorageCleanupRequest) -> Result<RawStorageCleanupPreview, String> {
-    let response = Request::post(&format!("{}/api/sys/raw-storage/cleanup", api_base()))
-        .header("Authorization", &auth_header()?)
+    let path = "/api/sys/raw-storage/cleanup";
+    let confirm_token = confirm_danger("POST", path, "очистка raw JSON").await?;
+    let mut request =
+        Request::post(&format!("{}{}", api_base(), path)).header("Authorization", &auth_header()?);
+    if let Some(token) = &confirm_token {
+        request = request.header(CONFIRM_TOKEN_HEADER, token);
+    }
+    let response = request
         .json(req)
         .map_err(|e| format!("Failed to serialize cleanup request: {}", e))?
         .send()
@@ -329,6 +329,12 @@ fn RawStorageContent() -> impl IntoView {
                             value=Signal::derive(move || status.get().map(|s| format_count(s.unreferenced_rows)).unwrap_or_else(|| "-".to_string()))
                             warn=Signal::derive(move || status.get().map(|s| s.unreferenced_rows > 0).unwrap_or(false))
                         />
+                        <StatTile
+                            icon_name="clock"
+                            label="История версий"
+                            value=Signal::derive(move || status.get().map(|s| format_count(s.history_rows)).unwrap_or_else(|| "-".to_string()))
+                            warn=Signal::derive(|| false)
+                        />
                     </div>
                 </section>
 
@@ -0,0 +1,176 @@
+use contracts::system::return_dispositions::{
+    RejectReturnDispositionRequest, ReturnDispositionDto, ReturnDispositionView,
+    SaveReturnDispositionRequest,
+};
+use contracts::system::s3::S3FileDto;
+use gloo_net::http::Request as GlooRequest;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{FormData, Request, RequestInit, RequestMode, Response};
+
+use crate::shared::api_utils::api_base;
+use crate::shared::auth_download::download_authenticated_file;
+use crate::system::auth::storage;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+/// Текст ошибки сервера (400/403/409 отдают его для пользователя).
+async fn error_text(response: gloo_net::http::Response, action: &str) -> String {
+    let text = response.text().await.unwrap_or_default();
+    if text.is_empty() {
+        format!("{}: HTTP {}", action, response.status())
+    } else {
+        text
+    }
+}
+
+/// Решение, фото и движения по документу возврата.
+pub async fn fetch_view(
+    entity_type: &str,
+    document_id: &str,
+) -> Result<ReturnDispositionView, String> {
+    let response = GlooRequest::get(&format!(
+        "{}/api/return-dispositions?entity_type={}&document_id={}",
+        api_base(),
+        entity_type,
+        document_id
+    ))
+    .header("Authorization", &auth_header()?)
+    .header("Cache-Control", "no-cache")
+    .send()
+    .await
+    .map_err(|e| format!("Failed to fetch return disposition: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "Failed to fetch return disposition").await);
+    }
+    response
+        .json::<ReturnDispositionView>()
+        .await
+        .map_err(|e| format!("Failed to parse return disposition: {}", e))
+}
+
+pub async fn save(request: &SaveReturnDispositionRequest) -> Result<ReturnDispositionDto, String> {
+    let response = GlooRequest::post(&format!("{}/api/return-dispositions", api_base()))
+        .header("Authorization", &auth_header()?)
+        .json(request)
+        .map_err(|e| format!("Failed to serialize return disposition: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to save return disposition: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "Failed to save return disposition").await);
+    }
+    response
+        .json::<ReturnDispositionDto>()
+        .await
+        .map_err(|e| format!("Failed to parse return disposition: {}", e))
+}
+
+/// Переход `submit` / `approve` / `reject` (для `reject` — с причиной).
+async fn transition(
+    id: &str,
+    action: &str,
+    reject: Option<RejectReturnDispositionRequest>,
+) -> Result<ReturnDispositionDto, String> {
+    let url = format!("{}/api/return-dispositions/{}/{}", api_base(), id, action);
+    let builder = GlooRequest::post(&url).header("Authorization", &auth_header()?);
+    let sent = match reject {
+        Some(body) => {
+            builder
+                .json(&body)
+                .map_err(|e| format!("Failed to serialize request: {}", e))?
+                .send()
+                .await
+        }
+        None => builder.send().await,
+    };
+    let response = sent.map_err(|e| format!("Failed to {} return disposition: {}", action, e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "Return disposition action failed").await);
+    }
+    response
+        .json::<ReturnDispositionDto>()
+        .await
+        .map_err(|e| format!("Failed to parse return disposition: {}", e))
+}
+
+pub async fn submit(id: &str) -> Result<ReturnDispositionDto, String> {
+    transition(id, "submit", None).await
+}
+
+pub async fn approve(id: &str) -> Result<ReturnDispositionDto, String> {
+    transition(id, "approve", None).await
+}
+
+pub async fn reject(id: &str, reason: String) -> Result<ReturnDispositionDto, String> {
+    transition(
+        id,
+        "reject",
+        Some(RejectReturnDispositionRequest { reason }),
+    )
+    .await
+}
+
+/// Загрузка фото возврата (привязывается к документу возврата).
+pub async fn upload_photo(
+    entity_type: &str,
+    document_id: &str,
+    file: web_sys::File,
+) -> Result<S3FileDto, String> {
+    let form_data = FormData::new().map_err(|e| format!("{e:?}"))?;
+    form_data
+        .append_with_blob("file", &file)
+        .map_err(|e| format!("{e:?}"))?;
+
+    let opts = RequestInit::new();
+    opts.set_method("POST");
+    opts.set_mode(RequestMode::Cors);
+    opts.set_body(&form_data);
+
+    let url = format!(
+        "{}/api/return-dispositions/photos?entity_type={}&document_id={}",
+        api_base(),
+        entity_type,
+        document_id
+    );
+    let request = Request::new_with_str_and_init(&url, &opts).map_err(|e| format!("{e:?}"))?;
+    request
+        .headers()
+        .set("Authorization", &auth_header()?)
+        .map_err(|e| format!("{e:?}"))?;
+
+    let window = web_sys::window().ok_or_else(|| "No window object".to_string())?;
+    let response_value = JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(|e| format!("Upload request failed: {e:?}"))?;
+    let response: Response = response_value
+        .dyn_into()
+        .map_err(|e| format!("Invalid upload response: {e:?}"))?;
+
+    let text = JsFuture::from(response.text().map_err(|e| format!("{e:?}"))?)
+        .await
+        .map_err(|e| format!("{e:?}"))?
+        .as_string()
+        .unwrap_or_default();
+    if !response.ok() {
+        return Err(if text.is_empty() {
+            format!("Upload failed: HTTP {}", response.status())
+        } else {
+            text
+        });
+    }
+    serde_json::from_str::<S3FileDto>(&text)
+        .map_err(|e| format!("Failed to parse upload response: {}", e))
+}
+
+pub async fn download_photo(file_id: &str, filename: &str) -> Result<(), String> {
+    let url = format!("{}/api/return-dispositions/photos/{}", api_base(), file_id);
+    download_authenticated_file(&url, filename).await
+}
@@ -0,0 +1,310 @@
+use contracts::system::return_dispositions::{
+    ReturnDecision, ReturnDispositionStatus, ReturnDispositionView, SaveReturnDispositionRequest,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::*;
+use wasm_bindgen::JsCast;
+
+use crate::shared::components::card_animated::CardAnimated;
+use crate::shared::date_utils::format_datetime_utc_local;
+use crate::shared::icons::icon;
+use crate::system::return_dispositions::api;
+
+fn fmt_dt(iso: &str) -> String {
+    format_datetime_utc_local(iso, "%d.%m.%Y %H:%M")
+}
+
+fn status_color(status: ReturnDispositionStatus) -> BadgeColor {
+    match status {
+        ReturnDispositionStatus::Draft => BadgeColor::Informative,
+        ReturnDispositionStatus::PendingApproval => BadgeColor::Warning,
+        ReturnDispositionStatus::Approved => BadgeColor::Success,
+        ReturnDispositionStatus::Rejected => BadgeColor::Danger,
+    }
+}
+
+/// Решение по физическому возврату в карточке документа: выбор решения, фото,
+/// отправка на утверждение, утверждение/отклонение и движения по складу.
+#[component]
+pub fn ReturnDispositionPanel(
+    /// Агрегат документа (`a009_ozon_returns`, `a016_ym_returns`, `a032_wb_returns_claims`)
+    entity_type: &'static str,
+    document_id: String,
+) -> impl IntoView {
+    let document_id = StoredValue::new(document_id);
+    let view_state = RwSignal::new(None::<ReturnDispositionView>);
+    let decision = RwSignal::new(ReturnDecision::ReturnToStock.code().to_string());
+    let comment = RwSignal::new(String::new());
+    let reject_reason = RwSignal::new(String::new());
+    let busy = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    let reload = move || {
+        spawn_local(async move {
+            match api::fetch_view(entity_type, &document_id.get_value()).await {
+                Ok(data) => {
+                    if let Some(d) = &data.disposition {
+                        decision.set(d.decision.code().to_string());
+                        comment.set(d.comment.clone().unwrap_or_default());
+                    }
+                    view_state.set(Some(data));
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    };
+    reload();
+
+    // Выполняет действие и перечитывает состояние панели
+    let run = move |action: &'static str| {
+        let disposition_id = view_state
+            .get_untracked()
+            .and_then(|v| v.disposition)
+            .map(|d| d.id);
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            let result = match (action, disposition_id) {
+                ("save", _) => {
+                    let request = SaveReturnDispositionRequest {
+                        entity_type: entity_type.to_string(),
+                        document_id: document_id.get_value(),
+                        decision: ReturnDecision::from_code(&decision.get_untracked())
+                            .unwrap_or(ReturnDecision::ReturnToStock),
+                        comment: Some(comment.get_untracked()),
+                    };
+                    api::save(&request).await.map(|_| ())
+                }
+                ("submit", Some(id)) => api::submit(&id).await.map(|_| ()),
+                ("approve", Some(id)) => api::approve(&id).await.map(|_| ()),
+                ("reject", Some(id)) => api::reject(&id, reject_reason.get_untracked())
+                    .await
+                    .map(|_| reject_reason.set(String::new())),
+                _ => Err("Сначала сохраните решение".to_string()),
+            };
+            match result {
+                Ok(()) => reload(),
+                Err(e) => error.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    let on_photo = move |ev: leptos::ev::Event| {
+        let file = ev
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+            .and_then(|input| input.files())
+            .and_then(|list| list.get(0));
+        let Some(file) = file else {
+            return;
+        };
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::upload_photo(entity_type, &document_id.get_value(), file).await {
+                Ok(_) => reload(),
+                Err(e) => error.set(Some(e)),
+            }
+            busy.set(false);
+        });
+    };
+
+    view! {
+        <CardAnimated delay_ms=0 nav_id="return_disposition">
+            <h4 class="details-section__title">"Решение по возврату"</h4>
+
+            {move || error.get().map(|e| view! { <div class="alert alert--error">{e}</div> })}
+
+            {move || {
+                let Some(state) = view_state.get() else {
+                    return view! { <div class="text-muted">"Загрузка..."</div> }.into_any();
+                };
+                let status = state.disposition.as_ref().map(|d| d.status);
+                let editable = status.map(|s| s.is_editable()).unwrap_or(true);
+                let pending = status == Some(ReturnDispositionStatus::PendingApproval);
+                let can_submit = status == Some(ReturnDispositionStatus::Draft)
+                    && !state.photos.is_empty();
+
+                let header = state.disposition.clone().map(|d| view! {
+                    <div style="display: flex; gap: var(--spacing-sm); align-items: center; flex-wrap: wrap;">
+                        <Badge appearance=BadgeAppearance::Tint color=status_color(d.status)>
+                            {d.status.label()}
+                        </Badge>
+                        <span class="text-muted" style="font-size: 12px;">
+                            {format!("{} · {}", d.created_by, fmt_dt(&d.created_at))}
+                        </span>
+                        {d.decided_by.clone().map(|who| view! {
+                            <span class="text-muted" style="font-size: 12px;">
+                                {format!(
+                                    "Решил: {} · {}",
+                                    who,
+                                    d.decided_at.as_deref().map(fmt_dt).unwrap_or_default()
+                                )}
+                            </span>
+                        })}
+                        {d.reject_reason.clone().map(|reason| view! {
+                            <span style="color: var(--color-error);">
+                                {format!("Причина отклонения: {}", reason)}
+                            </span>
+                        })}
+                    </div>
+                });
+
+                let photos = state.photos.clone();
+                let ledger = state.ledger.clone();
+                let can_approve = state.can_approve;
+
+                view! {
+                    <div style="display: flex; flex-direction: column; gap: var(--spacing-sm);">
+                        {header}
+
+                        <div style="display: grid; grid-template-columns: 220px 1fr; gap: var(--spacing-sm);">
+                            <select
+                                class="form__select"
+                                prop:value=move || decision.get()
+                                on:change=move |ev| decision.set(event_target_value(&ev))
+                                disabled=!editable
+                            >
+                                {ReturnDecision::ALL
+                                    .into_iter()
+                                    .map(|d| view! { <option value=d.code()>{d.label()}</option> })
+                                    .collect_view()}
+                            </select>
+                            <input
+                                class="form__input"
+                                type="text"
+                                placeholder="Комментарий (состояние товара, упаковки)"
+                                prop:value=move || comment.get()
+                                on:input=move |ev| comment.set(event_target_value(&ev))
+                                disabled=!editable
+                            />
+                        </div>
+
+                        <div style="display: flex; gap: var(--spacing-sm); align-items: center; flex-wrap: wrap;">
+                            <span class="form__label">{format!("Фото ({})", photos.len())}</span>
+                            {photos
+                                .into_iter()
+                                .map(|photo| {
+                                    let id = photo.id.clone();
+                                    let name = photo.original_filename.clone();
+                                    view! {
+                                        <Button
+                                            appearance=ButtonAppearance::Subtle
+                                            size=ButtonSize::Small
+                                            on_click=move |_| {
+                                                let id = id.clone();
+                                                let name = name.clone();
+                                                spawn_local(async move {
+                                                    if let Err(e) = api::download_photo(&id, &name).await {
+                                                        error.set(Some(e));
+                                                    }
+                                                });
+                                            }
+                                        >
+                                            {icon("paperclip")} " " {photo.original_filename.clone()}
+                                        </Button>
+                                    }
+                                })
+                                .collect_view()}
+                            <Show when=move || editable>
+                                <input
+                                    class="form__input form__input--file"
+                                    type="file"
+                                    accept="image/*"
+                                    on:change=on_photo
+                                    disabled=move || busy.get()
+                                />
+                            </Show>
+                        </div>
+
+                        <div style="display: flex; gap: var(--spacing-sm); flex-wrap: wrap;">
+                            <Show when=move || editable>
+                                <Button
+                                    appearance=ButtonAppearance::Secondary
+                                    on_click=move |_| run("save")
+                                    disabled=Signal::derive(move || busy.get())
+                                >
+                                    "Сохранить решение"
+                                </Button>
+                            </Show>
+                            <Show when=move || can_submit>
+                                <Button
+                                    appearance=ButtonAppearance::Primary
+                                    on_click=move |_| run("submit")
+                                    disabled=Signal::derive(move || busy.get())
+                                >
+                                    "На утверждение"
+                                </Button>
+                            </Show>
+                            <Show when=move || pending && can_approve>
+                                <Button
+                                    appearance=ButtonAppearance::Primary
+                                    on_click=move |_| run("approve")
+                                    disabled=Signal::derive(move || busy.get())
+                                >
+                                    {icon("check")} " Утвердить"
+                                </Button>
+                                <input
+                                    class="form__input"
+                                    type="text"
+                                    placeholder="Причина отклонения"
+                                    prop:value=move || reject_reason.get()
+                                    on:input=move |ev| reject_reason.set(event_target_value(&ev))
+                                />
+                                <Button
+                                    appearance=ButtonAppearance::Subtle
+                                    on_click=move |_| run("reject")
+                                    disabled=Signal::derive(move || {
+                                        busy.get() || reject_reason.get().trim().is_empty()
+                                    })
+                                >
+                                    "Отклонить"
+                                </Button>
+                            </Show>
+                        </div>
+
+                        {(!ledger.is_empty()).then(|| view! {
+                            <table class="table__data table--striped">
+                                <thead class="table__head">
+                                    <tr class="table__header-row">
+                                        <th class="table__header-cell">"Дата"</th>
+                                        <th class="table__header-cell">"Решение"</th>
+                                        <th class="table__header-cell">"Артикул"</th>
+                                        <th class="table__header-cell">"Товар"</th>
+                                        <th class="table__header-cell">"Кол-во"</th>
+                                        <th class="table__header-cell">"Остаток"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {ledger
+                                        .into_iter()
+                                        .map(|entry| {
+                                            let decision_label = ReturnDecision::from_code(&entry.decision)
+                                                .map(|d| d.label())
+                                                .unwrap_or_default();
+                                            view! {
+                                                <tr class="table__row">
+                                                    <td class="table__cell">{entry.entry_date}</td>
+                                                    <td class="table__cell">{decision_label}</td>
+                                                    <td class="table__cell">{entry.article}</td>
+                                                    <td class="table__cell">{entry.product_name}</td>
+                                                    <td class="table__cell table__cell--right">{entry.quantity}</td>
+                                                    <td class="table__cell table__cell--right">
+                                                        {format!("{:+}", entry.stock_delta)}
+                                                    </td>
+                                                </tr>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </tbody>
+                            </table>
+                        })}
+                    </div>
+                }
+                .into_any()
+            }}
+        </CardAnimated>
+    }
+}
@@ -1,5 +1,5 @@
 use contracts::system::access::ScopeDescriptorDto;
-use contracts::system::roles::{CreateRoleDto, Role, RoleScopeAccess, UpdateRoleDto};
+use contracts::system::roles::{CreateRoleDto, Role, RolePreview, RoleScopeAccess, UpdateRoleDto};
 use gloo_net::http::Request;
 
 use crate::shared::api_utils::api_base;
@@ -51,6 +51,29 @@ pub async fn create_role(dto: CreateRoleDto) -> Result<String, String> {
     Ok(result["id"].as_str().unwrap_or("").to_string())
 }
 
+/// Effective permissions of a role for the "view as role" preview.
+pub async fn fetch_role_preview(id: &str) -> Result<RolePreview, String> {
+    let auth_header = get_auth_header().ok_or("Not authenticated")?;
+
+    let response = Request::get(&format!("{}/api/system/roles/{}/preview", api_base(), id))
+        .header("Authorization", &auth_header)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch role preview: {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json::<RolePreview>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
 pub async fn update_role(dto: UpdateRoleDto) -> Result<(), String> {
     let auth_header = get_auth_header().ok_or("Not authenticated")?;
 
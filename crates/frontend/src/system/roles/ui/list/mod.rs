@@ -8,6 +8,7 @@ use crate::shared::components::pagination_controls::PaginationControls;
 use crate::shared::icons::icon;
 use crate::shared::page_frame::PageFrame;
 use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::auth::context::{start_role_preview, use_auth};
 use crate::system::auth::guard::RequireAdmin;
 use crate::system::roles::api;
 
@@ -128,6 +129,19 @@ fn RolesList() -> impl IntoView {
         }
     };
 
+    // «Просмотр как роль»: права роли подставляются в контекст авторизации,
+    // сессия и токен администратора не меняются.
+    let (_, set_auth_state) = use_auth();
+    let preview_as_role = move |role_id: String| {
+        set_error.set(None);
+        spawn_local(async move {
+            match api::fetch_role_preview(&role_id).await {
+                Ok(preview) => start_role_preview(set_auth_state, preview),
+                Err(e) => set_error.set(Some(format!("Не удалось включить просмотр: {}", e))),
+            }
+        });
+    };
+
     view! {
         <PageFrame page_id="sys_roles--list" category=PAGE_CAT_SYSTEM>
             <div class="page__header">
@@ -226,6 +240,8 @@ fn RolesList() -> impl IntoView {
                                     let role_id_for_delete = role.id.clone();
                                     let role_for_edit = role.clone();
                                     let is_system = role.is_system;
+                                    let can_preview = role.code != "admin";
+                                    let role_id_for_preview = role.id.clone();
                                     view! {
                                         <TableRow>
                                             <TableCell>
@@ -256,6 +272,15 @@ fn RolesList() -> impl IntoView {
                                                     >
                                                         {icon("shield")}
                                                     </Button>
+                                                    {can_preview.then(|| view! {
+                                                        <Button
+                                                            appearance=ButtonAppearance::Subtle
+                                                            on_click=move |_| preview_as_role(role_id_for_preview.clone())
+                                                            attr:title="Просмотр как роль"
+                                                        >
+                                                            {icon("eye")}
+                                                        </Button>
+                                                    })}
                                                     {if !is_system {
                                                         view! {
                                                             <>
@@ -1,4 +1,6 @@
-use contracts::system::s3::{S3FileCategory, S3FileListResponse, S3UploadResponse};
+use contracts::system::s3::{
+    S3FileCategory, S3FileListResponse, S3TextSearchResponse, S3UploadResponse,
+};
 use gloo_net::http::Request as GlooRequest;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
@@ -40,14 +42,24 @@ pub async fn fetch_files(category: Option<S3FileCategory>) -> Result<S3FileListR
         .map_err(|e| format!("Failed to parse S3 files: {}", e))
 }
 
+/// `document` — привязка к документу `(document_type, document_id)`.
 pub async fn upload_file(
     category: S3FileCategory,
     file: web_sys::File,
+    document: Option<(String, String)>,
 ) -> Result<S3UploadResponse, String> {
     let form_data = FormData::new().map_err(|e| format!("{e:?}"))?;
     form_data
         .append_with_str("category", category.as_str())
         .map_err(|e| format!("{e:?}"))?;
+    if let Some((document_type, document_id)) = document {
+        form_data
+            .append_with_str("document_type", &document_type)
+            .map_err(|e| format!("{e:?}"))?;
+        form_data
+            .append_with_str("document_id", &document_id)
+            .map_err(|e| format!("{e:?}"))?;
+    }
     form_data
         .append_with_blob("file", &file)
         .map_err(|e| format!("{e:?}"))?;
@@ -86,6 +98,34 @@ pub async fn upload_file(
         .map_err(|e| format!("Failed to parse upload response: {}", e))
 }
 
+/// Поиск по распознанному тексту вложений.
+pub async fn search_text(query: &str) -> Result<S3TextSearchResponse, String> {
+    let url = format!(
+        "{}/api/sys/s3/search?q={}",
+        api_base(),
+        urlencoding::encode(query)
+    );
+
+    let response = GlooRequest::get(&url)
+        .header("Authorization", &auth_header()?)
+        .header("Cache-Control", "no-cache")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to search attachments: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json::<S3TextSearchResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse search results: {}", e))
+}
+
 pub async fn delete_file(id: &str) -> Result<(), String> {
     let response = GlooRequest::delete(&format!("{}/api/sys/s3/files/{}", api_base(), id))
         .header("Authorization", &auth_header()?)
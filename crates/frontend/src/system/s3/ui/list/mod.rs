@@ -19,6 +19,7 @@ use crate::shared::page_standard::PAGE_CAT_SYSTEM;
 use crate::shared::table_utils::init_column_resize;
 use crate::system::auth::guard::RequireAdmin;
 use crate::system::s3::api;
+use crate::system::s3::ui::text_search::S3TextSearchPanel;
 
 const TABLE_ID: &str = "sys-s3-files-table";
 const COLUMN_WIDTHS_KEY: &str = "sys_s3_files_column_widths";
@@ -307,6 +308,8 @@ fn S3FilesList() -> impl IntoView {
                     })
                 }}
 
+                <S3TextSearchPanel />
+
                 <div class="table-wrapper">
                     <TableCrosshairHighlight table_id=TABLE_ID.to_string() />
 
@@ -389,6 +392,8 @@ fn S3UploadDialog(show: RwSignal<bool>, on_uploaded: Callback<()>) -> impl IntoV
     let upload_category = RwSignal::new(S3FileCategory::Documents.as_str().to_string());
     let selected_file = StoredValue::new_local(None::<web_sys::File>);
     let file_info = RwSignal::new(None::<(String, u64)>);
+    let document_type = RwSignal::new(String::new());
+    let document_id = RwSignal::new(String::new());
     let (uploading, set_uploading) = signal(false);
     let (result, set_result) = signal::<Option<Result<String, String>>>(None);
 
@@ -397,6 +402,8 @@ fn S3UploadDialog(show: RwSignal<bool>, on_uploaded: Callback<()>) -> impl IntoV
         file_info.set(None);
         set_result.set(None);
         upload_category.set(S3FileCategory::Documents.as_str().to_string());
+        document_type.set(String::new());
+        document_id.set(String::new());
         set_uploading.set(false);
         show.set(false);
     });
@@ -408,10 +415,19 @@ fn S3UploadDialog(show: RwSignal<bool>, on_uploaded: Callback<()>) -> impl IntoV
         };
         let file_name = file.name();
         let selected_category = S3FileCategory::from(upload_category.get_untracked().as_str());
+        let doc_type = document_type.get_untracked().trim().to_string();
+        let doc_id = document_id.get_untracked().trim().to_string();
+        if doc_type.is_empty() != doc_id.is_empty() {
+            set_result.set(Some(Err(
+                "Укажите и тип, и ID документа (или оставьте оба поля пустыми)".to_string(),
+            )));
+            return;
+        }
+        let document = (!doc_type.is_empty()).then_some((doc_type, doc_id));
         set_uploading.set(true);
         set_result.set(None);
         spawn_local(async move {
-            match api::upload_file(selected_category, file).await {
+            match api::upload_file(selected_category, file, document).await {
                 Ok(_) => {
                     set_result.set(Some(Ok(format!("Файл «{file_name}» загружен"))));
                     selected_file.set_value(None);
@@ -470,6 +486,24 @@ fn S3UploadDialog(show: RwSignal<bool>, on_uploaded: Callback<()>) -> impl IntoV
                         />
                     </Flex>
 
+                    <Flex vertical=true gap=FlexGap::Small>
+                        <Label>"Документ (необязательно):"</Label>
+                        <input
+                            class="form__input"
+                            type="text"
+                            placeholder="Тип, например a023_purchase_of_goods"
+                            prop:value=move || document_type.get()
+                            on:input=move |ev| document_type.set(event_target_value(&ev))
+                        />
+                        <input
+                            class="form__input"
+                            type="text"
+                            placeholder="ID документа"
+                            prop:value=move || document_id.get()
+                            on:input=move |ev| document_id.set(event_target_value(&ev))
+                        />
+                    </Flex>
+
                     {move || file_info.get().map(|(name, size)| view! {
                         <div class="text-muted" style="font-size: 13px;">
                             "Выбран файл: "
@@ -656,6 +690,7 @@ fn category_badge(category: S3FileCategory) -> impl IntoView {
         S3FileCategory::Plugins => BadgeColor::Success,
         S3FileCategory::Backups => BadgeColor::Warning,
         S3FileCategory::ConferenceAudio => BadgeColor::Important,
+        S3FileCategory::Exports => BadgeColor::Informative,
         S3FileCategory::Other => BadgeColor::Subtle,
     };
 
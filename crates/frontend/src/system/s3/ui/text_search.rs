@@ -0,0 +1,130 @@
+use contracts::system::s3::S3TextSearchResponse;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::*;
+
+use crate::layout::global_context::AppGlobalContext;
+use crate::shared::date_utils::format_datetime;
+use crate::shared::icons::icon;
+use crate::system::s3::api;
+
+/// Поиск по распознанному тексту вложений: находит файл и документ, к которому он приложен.
+#[component]
+pub fn S3TextSearchPanel() -> impl IntoView {
+    let tabs = use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
+    let query = RwSignal::new(String::new());
+    let (result, set_result) = signal::<Option<S3TextSearchResponse>>(None);
+    let (searching, set_searching) = signal(false);
+    let (error, set_error) = signal::<Option<String>>(None);
+
+    let run_search = move || {
+        let text = query.get_untracked();
+        if text.trim().is_empty() {
+            set_result.set(None);
+            return;
+        }
+        set_searching.set(true);
+        set_error.set(None);
+        spawn_local(async move {
+            match api::search_text(&text).await {
+                Ok(response) => set_result.set(Some(response)),
+                Err(err) => set_error.set(Some(err)),
+            }
+            set_searching.set(false);
+        });
+    };
+
+    view! {
+        <div class="filter-panel">
+            <div class="filter-panel-content">
+                <div style="display: flex; gap: 12px; align-items: end; flex-wrap: wrap;">
+                    <div style="flex: 1; min-width: 260px; max-width: 520px;">
+                        <Flex vertical=true gap=FlexGap::Small>
+                            <Label>"Поиск по тексту вложений:"</Label>
+                            <input
+                                class="form__input"
+                                type="text"
+                                placeholder="Номер акта, контрагент, сумма..."
+                                prop:value=move || query.get()
+                                on:input=move |ev| query.set(event_target_value(&ev))
+                                on:keydown=move |ev| {
+                                    if ev.key() == "Enter" {
+                                        run_search();
+                                    }
+                                }
+                            />
+                        </Flex>
+                    </div>
+                    <button
+                        class="button button--secondary"
+                        on:click=move |_| run_search()
+                        disabled=move || searching.get()
+                    >
+                        {icon("search")}
+                        {move || if searching.get() { "Поиск..." } else { "Найти" }}
+                    </button>
+                </div>
+
+                {move || error.get().map(|err| view! { <div class="alert alert--error">{err}</div> })}
+
+                {move || result.get().map(|response| {
+                    let pending = (response.pending_files > 0).then(|| view! {
+                        <div class="text-muted" style="font-size: 12px;">
+                            {format!("Ожидают распознавания: {} файл(ов)", response.pending_files)}
+                        </div>
+                    });
+                    let hits = if response.items.is_empty() {
+                        view! { <div class="text-muted">"Совпадений не найдено"</div> }.into_any()
+                    } else {
+                        response.items.into_iter().map(|hit| {
+                            let document = hit.document_type.clone().zip(hit.document_id.clone());
+                            let download_id = hit.file_id.clone();
+                            let download_name = hit.original_filename.clone();
+                            view! {
+                                <div style="display: grid; gap: 2px; padding: 6px 0; border-bottom: 1px solid var(--color-border);">
+                                    <div style="display: flex; gap: 8px; align-items: center;">
+                                        <button
+                                            class="button button--small button--secondary"
+                                            title="Скачать"
+                                            on:click=move |_| {
+                                                let id = download_id.clone();
+                                                let name = download_name.clone();
+                                                spawn_local(async move {
+                                                    if let Err(err) = api::download_file(&id, &name).await {
+                                                        set_error.set(Some(err));
+                                                    }
+                                                });
+                                            }
+                                        >
+                                            {icon("download")}
+                                        </button>
+                                        <span style="font-weight: 600;">{hit.original_filename.clone()}</span>
+                                        {document.map(|(document_type, document_id)| {
+                                            let tab_key = format!("{}_details_{}", document_type, document_id);
+                                            let title = format!("{} {}", document_type, document_id);
+                                            view! {
+                                                <button
+                                                    class="button button--small button--ghost"
+                                                    title="Открыть документ"
+                                                    on:click=move |_| tabs.open_tab(&tab_key, &title)
+                                                >
+                                                    {icon("file-text")}
+                                                    {format!("{} · {}", document_type, document_id)}
+                                                </button>
+                                            }
+                                        })}
+                                        <span class="text-muted" style="font-size: 12px;">
+                                            {format_datetime(&hit.created_at)}
+                                        </span>
+                                    </div>
+                                    <div class="text-muted" style="font-size: 13px;">{hit.snippet}</div>
+                                </div>
+                            }
+                        }).collect_view().into_any()
+                    };
+                    view! { <div>{pending}{hits}</div> }
+                })}
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,81 @@
+use contracts::system::scheduled_posts::{SchedulePostRequest, ScheduledPostDto};
+use gloo_net::http::Request;
+
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+/// Текст ошибки сервера (400/403/409 отдают его для пользователя).
+async fn error_text(response: gloo_net::http::Response, action: &str) -> String {
+    let text = response.text().await.unwrap_or_default();
+    if text.is_empty() {
+        format!("{}: HTTP {}", action, response.status())
+    } else {
+        text
+    }
+}
+
+/// Ожидающее задание отложенного проведения документа.
+pub async fn fetch_pending(
+    entity_type: &str,
+    document_id: &str,
+) -> Result<Option<ScheduledPostDto>, String> {
+    let response = Request::get(&format!(
+        "{}/api/scheduled-posts?entity_type={}&document_id={}",
+        api_base(),
+        entity_type,
+        document_id
+    ))
+    .header("Authorization", &auth_header()?)
+    .header("Cache-Control", "no-cache")
+    .send()
+    .await
+    .map_err(|e| format!("Failed to fetch scheduled posting: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch scheduled posting: HTTP {}",
+            response.status()
+        ));
+    }
+    response
+        .json::<Option<ScheduledPostDto>>()
+        .await
+        .map_err(|e| format!("Failed to parse scheduled posting: {}", e))
+}
+
+pub async fn schedule_post(request: &SchedulePostRequest) -> Result<ScheduledPostDto, String> {
+    let response = Request::post(&format!("{}/api/scheduled-posts", api_base()))
+        .header("Authorization", &auth_header()?)
+        .json(request)
+        .map_err(|e| format!("Failed to serialize scheduled posting: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to schedule posting: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "Failed to schedule posting").await);
+    }
+    response
+        .json::<ScheduledPostDto>()
+        .await
+        .map_err(|e| format!("Failed to parse scheduled posting: {}", e))
+}
+
+pub async fn cancel_scheduled_post(id: &str) -> Result<(), String> {
+    let response = Request::delete(&format!("{}/api/scheduled-posts/{}", api_base(), id))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to cancel scheduled posting: {}", e))?;
+
+    if !response.ok() {
+        return Err(error_text(response, "Failed to cancel scheduled posting").await);
+    }
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use chrono::{FixedOffset, NaiveDateTime, TimeZone};
+use contracts::system::scheduled_posts::{SchedulePostRequest, ScheduledPostDto};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::*;
+
+use crate::shared::date_utils::{format_datetime_utc_local, TZ_OFFSET_HOURS};
+use crate::shared::icons::icon;
+use crate::system::scheduled_posts::api;
+
+/// Значение `<input type="datetime-local">` (время UI, см. `TZ_OFFSET_HOURS`) → RFC3339.
+fn local_input_to_rfc3339(value: &str) -> Option<String> {
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M").ok()?;
+    FixedOffset::east_opt(TZ_OFFSET_HOURS * 3600)?
+        .from_local_datetime(&naive)
+        .single()
+        .map(|dt| dt.to_rfc3339())
+}
+
+fn alert(message: &str) {
+    if let Some(window) = web_sys::window() {
+        let _ = window.alert_with_message(message);
+    }
+}
+
+/// Отложенное проведение в шапке документа: «В очереди на проведение: …» с отменой,
+/// либо кнопка «Провести позже» с выбором времени.
+#[component]
+pub fn ScheduledPostControl(
+    /// Агрегат документа (`a012_wb_sales`, `a013_ym_order`, `a016_ym_returns`)
+    entity_type: &'static str,
+    /// id документа; пока `None`, контрол не показывается
+    #[prop(into)]
+    document_id: Signal<Option<String>>,
+) -> impl IntoView {
+    let pending = RwSignal::new(None::<ScheduledPostDto>);
+    let editing = RwSignal::new(false);
+    let post_at = RwSignal::new(String::new());
+    let busy = RwSignal::new(false);
+
+    Effect::new(move |_| {
+        pending.set(None);
+        let Some(id) = document_id.get() else {
+            return;
+        };
+        spawn_local(async move {
+            match api::fetch_pending(entity_type, &id).await {
+                Ok(job) => pending.set(job),
+                Err(e) => leptos::logging::log!("{}", e),
+            }
+        });
+    });
+
+    let on_schedule = move |_| {
+        let Some(document_id) = document_id.get_untracked() else {
+            return;
+        };
+        let Some(post_at) = local_input_to_rfc3339(&post_at.get_untracked()) else {
+            alert("Укажите дату и время проведения");
+            return;
+        };
+        let request = SchedulePostRequest {
+            entity_type: entity_type.to_string(),
+            document_id,
+            post_at,
+        };
+        busy.set(true);
+        spawn_local(async move {
+            match api::schedule_post(&request).await {
+                Ok(job) => {
+                    pending.set(Some(job));
+                    editing.set(false);
+                }
+                Err(e) => alert(&e),
+            }
+            busy.set(false);
+        });
+    };
+
+    let on_cancel = move |_| {
+        let Some(job) = pending.get_untracked() else {
+            return;
+        };
+        busy.set(true);
+        spawn_local(async move {
+            match api::cancel_scheduled_post(&job.id).await {
+                Ok(()) => pending.set(None),
+                Err(e) => alert(&e),
+            }
+            busy.set(false);
+        });
+    };
+
+    view! {
+        <Show when=move || document_id.get().is_some()>
+            {move || match pending.get() {
+                Some(job) => view! {
+                    <Badge appearance=BadgeAppearance::Tint color=BadgeColor::Informative>
+                        {format!(
+                            "В очереди на проведение: {}",
+                            format_datetime_utc_local(&job.post_at, "%d.%m.%Y %H:%M")
+                        )}
+                    </Badge>
+                    <Button
+                        appearance=ButtonAppearance::Subtle
+                        size=ButtonSize::Small
+                        on_click=on_cancel
+                        disabled=Signal::derive(move || busy.get())
+                    >
+                        {icon("x")} " Отменить"
+                    </Button>
+                }
+                .into_any(),
+                None if editing.get() => view! {
+                    <input
+                        type="datetime-local"
+                        class="form__input"
+                        prop:value=move || post_at.get()
+                        on:input=move |ev| post_at.set(event_target_value(&ev))
+                    />
+                    <Button
+                        appearance=ButtonAppearance::Primary
+                        size=ButtonSize::Small
+                        on_click=on_schedule
+                        disabled=Signal::derive(move || busy.get())
+                    >
+                        "В очередь"
+                    </Button>
+                    <Button
+                        appearance=ButtonAppearance::Subtle
+                        size=ButtonSize::Small
+                        on_click=move |_| editing.set(false)
+                    >
+                        "Отмена"
+                    </Button>
+                }
+                .into_any(),
+                None => view! {
+                    <Button
+                        appearance=ButtonAppearance::Subtle
+                        size=ButtonSize::Small
+                        on_click=move |_| editing.set(true)
+                    >
+                        {icon("clock")} " Провести позже"
+                    </Button>
+                }
+                .into_any(),
+            }}
+        </Show>
+    }
+}
@@ -0,0 +1,47 @@
+use contracts::system::log_viewer::{LogQuery, LogQueryResponse};
+use gloo_net::http::Request;
+
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+
+fn get_auth_header() -> Option<String> {
+    storage::get_access_token().map(|token| format!("Bearer {}", token))
+}
+
+pub async fn fetch_server_log(query: &LogQuery) -> Result<LogQueryResponse, String> {
+    let auth_header = get_auth_header().ok_or("Not authenticated")?;
+
+    let mut params = Vec::new();
+    for (key, value) in [
+        ("level", &query.level),
+        ("module", &query.module),
+        ("q", &query.q),
+        ("request_id", &query.request_id),
+    ] {
+        if let Some(value) = value.as_deref().filter(|v| !v.trim().is_empty()) {
+            params.push(format!("{}={}", key, urlencoding::encode(value.trim())));
+        }
+    }
+    if let Some(limit) = query.limit {
+        params.push(format!("limit={}", limit));
+    }
+
+    let response = Request::get(&format!(
+        "{}/api/sys/server-log?{}",
+        api_base(),
+        params.join("&")
+    ))
+    .header("Authorization", &auth_header)
+    .send()
+    .await
+    .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!("Failed to fetch server log: {}", response.status()));
+    }
+
+    response
+        .json::<LogQueryResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
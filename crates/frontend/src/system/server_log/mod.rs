@@ -0,0 +1,257 @@
+//! «Журнал сервера»: хвост `backend.log` с фильтрами по уровню, модулю, тексту и
+//! correlation id запроса — разбор инцидентов без SSH-доступа к серверу.
+
+pub mod api;
+
+use chrono::Utc;
+use contracts::system::log_viewer::{LogEntry, LogQuery, LogQueryResponse, LOG_LEVELS};
+use gloo_timers::future::TimeoutFuture;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::{Button, ButtonAppearance};
+
+use crate::shared::date_utils::format_utc_local;
+use crate::shared::icons::icon;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::auth::guard::RequireAdmin;
+
+/// Период автообновления («хвост» журнала).
+const TAIL_POLL_MS: u32 = 5000;
+
+fn level_color(level: &str) -> &'static str {
+    match level {
+        "ERROR" => "var(--color-error,#dc2626)",
+        "WARN" => "var(--color-warning,#d97706)",
+        "INFO" => "var(--color-info-600,#0284c7)",
+        _ => "var(--color-text-secondary)",
+    }
+}
+
+fn format_timestamp(raw: Option<&str>) -> String {
+    raw.and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+        .map(|dt| format_utc_local(&dt.with_timezone(&Utc), "%d.%m %H:%M:%S"))
+        .unwrap_or_default()
+}
+
+#[component]
+pub fn ServerLogPage() -> impl IntoView {
+    view! {
+        <RequireAdmin>
+            <ServerLogPageInner />
+        </RequireAdmin>
+    }
+}
+
+#[component]
+fn ServerLogPageInner() -> impl IntoView {
+    let level = RwSignal::new("INFO".to_string());
+    let module = RwSignal::new(String::new());
+    let search = RwSignal::new(String::new());
+    let request_id = RwSignal::new(String::new());
+    let tail = RwSignal::new(false);
+
+    let result: RwSignal<Option<LogQueryResponse>> = RwSignal::new(None);
+    let (error, set_error) = signal::<Option<String>>(None);
+    let (loading, set_loading) = signal(false);
+
+    let load = move || {
+        let query = LogQuery {
+            level: Some(level.get_untracked()),
+            module: Some(module.get_untracked()),
+            q: Some(search.get_untracked()),
+            request_id: Some(request_id.get_untracked()),
+            limit: Some(500),
+        };
+        set_loading.set(true);
+        spawn_local(async move {
+            match api::fetch_server_log(&query).await {
+                Ok(resp) => {
+                    result.set(Some(resp));
+                    set_error.set(None);
+                }
+                Err(e) => set_error.set(Some(format!("Ошибка загрузки журнала: {}", e))),
+            }
+            set_loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| {
+        // Уровень и correlation id применяются сразу, текстовые поля — по Enter
+        level.track();
+        request_id.track();
+        load();
+    });
+
+    let alive = StoredValue::new(true);
+    on_cleanup(move || alive.set_value(false));
+    spawn_local(async move {
+        loop {
+            TimeoutFuture::new(TAIL_POLL_MS).await;
+            if alive.try_get_value() != Some(true) {
+                break;
+            }
+            if tail.try_get_untracked() == Some(true) && loading.try_get_untracked() == Some(false)
+            {
+                load();
+            }
+        }
+    });
+
+    let on_enter = move |ev: leptos::ev::KeyboardEvent| {
+        if ev.key() == "Enter" {
+            load();
+        }
+    };
+
+    view! {
+        <PageFrame page_id="sys_server_log" category=PAGE_CAT_SYSTEM>
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Журнал сервера"</h1>
+                </div>
+                <div class="page__header-right">
+                    <label style="display:flex;align-items:center;gap:6px;font-size:0.85em;cursor:pointer;">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || tail.get()
+                            on:change=move |e| tail.set(event_target_checked(&e))
+                        />
+                        "Автообновление"
+                    </label>
+                    <Button
+                        appearance=ButtonAppearance::Secondary
+                        on_click=move |_| load()
+                        disabled=Signal::derive(move || loading.get())
+                    >
+                        {icon("refresh")}
+                        " Обновить"
+                    </Button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                <div style="display:flex;gap:8px;align-items:center;flex-wrap:wrap;margin-bottom:12px;">
+                    <select
+                        style="padding:6px 10px;border:1px solid var(--color-border);border-radius:4px;font-size:0.85em;"
+                        on:change=move |e| level.set(event_target_value(&e))
+                    >
+                        {LOG_LEVELS
+                            .iter()
+                            .map(|l| {
+                                let value = l.to_string();
+                                view! {
+                                    <option value=value.clone() selected=move || level.get() == value>
+                                        {format!("{} и важнее", l)}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                    <input
+                        type="text"
+                        placeholder="Модуль (system::tasks)"
+                        style="padding:6px 10px;border:1px solid var(--color-border);border-radius:4px;font-size:0.85em;min-width:200px;"
+                        prop:value=move || module.get()
+                        on:input=move |e| module.set(event_target_value(&e))
+                        on:keydown=on_enter
+                    />
+                    <input
+                        type="text"
+                        placeholder="Поиск по тексту..."
+                        style="padding:6px 10px;border:1px solid var(--color-border);border-radius:4px;font-size:0.85em;min-width:240px;"
+                        prop:value=move || search.get()
+                        on:input=move |e| search.set(event_target_value(&e))
+                        on:keydown=on_enter
+                    />
+                    <input
+                        type="text"
+                        placeholder="Correlation id (x-request-id)"
+                        style="padding:6px 10px;border:1px solid var(--color-border);border-radius:4px;font-size:0.85em;min-width:280px;font-family:monospace;"
+                        prop:value=move || request_id.get()
+                        on:change=move |e| request_id.set(event_target_value(&e))
+                    />
+                    {move || {
+                        result.get().map(|r| {
+                            view! {
+                                <span style="font-size:0.8em;color:var(--color-text-secondary);">
+                                    {format!(
+                                        "Найдено {}{} из {} записей · файл {} КБ",
+                                        r.entries.len(),
+                                        if r.truncated { "+" } else { "" },
+                                        r.scanned,
+                                        r.file_size / 1024
+                                    )}
+                                </span>
+                            }
+                        })
+                    }}
+                </div>
+
+                {move || error.get().map(|e| view! { <div class="alert alert--error">{e}</div> })}
+
+                <div style="overflow-x:auto;">
+                    <table style="border-collapse:collapse;font-size:0.82em;width:100%;min-width:800px;">
+                        <thead>
+                            <tr style="text-align:left;border-bottom:1px solid var(--color-border);">
+                                <th style="padding:6px 8px;width:110px;">"Время"</th>
+                                <th style="padding:6px 8px;width:60px;">"Уровень"</th>
+                                <th style="padding:6px 8px;width:240px;">"Модуль"</th>
+                                <th style="padding:6px 8px;width:120px;">"Запрос"</th>
+                                <th style="padding:6px 8px;">"Сообщение"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {move || {
+                                result
+                                    .get()
+                                    .map(|r| r.entries)
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .map(|entry| view! { <LogRow entry=entry request_id=request_id /> })
+                                    .collect_view()
+                            }}
+                        </tbody>
+                    </table>
+                </div>
+            </div>
+        </PageFrame>
+    }
+}
+
+#[component]
+fn LogRow(entry: LogEntry, request_id: RwSignal<String>) -> impl IntoView {
+    let color = level_color(&entry.level);
+    let time = format_timestamp(entry.timestamp.as_deref());
+    let correlation = entry.request_id.clone().map(|id| {
+        let short: String = id.chars().take(8).collect();
+        let title = id.clone();
+        view! {
+            <a
+                href="#"
+                style="font-family:monospace;"
+                title=title
+                on:click=move |ev| {
+                    ev.prevent_default();
+                    request_id.set(id.clone());
+                }
+            >
+                {short}
+            </a>
+        }
+    });
+
+    view! {
+        <tr style="border-bottom:1px solid var(--color-border);vertical-align:top;">
+            <td style="padding:4px 8px;white-space:nowrap;font-family:monospace;">{time}</td>
+            <td style=format!("padding:4px 8px;font-weight:600;color:{};", color)>{entry.level.clone()}</td>
+            <td style="padding:4px 8px;font-family:monospace;color:var(--color-text-secondary);word-break:break-all;">
+                {entry.target.clone()}
+            </td>
+            <td style="padding:4px 8px;">{correlation}</td>
+            <td style="padding:4px 8px;white-space:pre-wrap;word-break:break-word;font-family:monospace;">
+                {entry.message.clone()}
+            </td>
+        </tr>
+    }
+}
@@ -0,0 +1,56 @@
+use crate::shared::api_utils::api_base;
+use crate::system::auth::storage;
+use contracts::system::auth::OidcSettings;
+use gloo_net::http::Request;
+
+fn auth_header() -> Result<String, String> {
+    storage::get_access_token()
+        .map(|token| format!("Bearer {}", token))
+        .ok_or_else(|| "Not authenticated".to_string())
+}
+
+pub async fn fetch_settings() -> Result<OidcSettings, String> {
+    let response = Request::get(&format!("{}/api/sys/auth/oidc", api_base()))
+        .header("Authorization", &auth_header()?)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch OIDC settings: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch OIDC settings: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse OIDC settings: {}", e))
+}
+
+pub async fn save_settings(settings: &OidcSettings) -> Result<OidcSettings, String> {
+    let response = Request::put(&format!("{}/api/sys/auth/oidc", api_base()))
+        .header("Authorization", &auth_header()?)
+        .json(settings)
+        .map_err(|e| format!("Failed to serialize OIDC settings: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to save OIDC settings: {}", e))?;
+
+    if response.status() == 400 {
+        let message = response.text().await.unwrap_or_default();
+        return Err(format!("Проверьте настройки: {}", message));
+    }
+    if !response.ok() {
+        return Err(format!(
+            "Failed to save OIDC settings: HTTP {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse saved OIDC settings: {}", e))
+}
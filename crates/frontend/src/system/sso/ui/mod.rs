@@ -0,0 +1,339 @@
+use contracts::system::auth::{OidcRoleMapping, OidcSettings};
+use contracts::system::roles::Role;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use thaw::{Checkbox, Input};
+
+use crate::shared::api_utils::api_base;
+use crate::shared::icons::icon;
+use crate::shared::page_frame::PageFrame;
+use crate::shared::page_standard::PAGE_CAT_SYSTEM;
+use crate::system::auth::guard::RequireAdmin;
+use crate::system::roles::api::fetch_roles;
+use crate::system::sso::api;
+
+#[component]
+pub fn SsoSettingsPage() -> impl IntoView {
+    view! {
+        <RequireAdmin>
+            <SsoSettingsContent />
+        </RequireAdmin>
+    }
+}
+
+#[component]
+fn SsoSettingsContent() -> impl IntoView {
+    let loading = RwSignal::new(false);
+    let saving = RwSignal::new(false);
+    let error = RwSignal::<Option<String>>::new(None);
+    let notice = RwSignal::<Option<String>>::new(None);
+    let roles = RwSignal::<Vec<Role>>::new(Vec::new());
+
+    let enabled = RwSignal::new(false);
+    let display_name = RwSignal::new(String::new());
+    let issuer_url = RwSignal::new(String::new());
+    let client_id = RwSignal::new(String::new());
+    let client_secret = RwSignal::new(String::new());
+    let client_secret_set = RwSignal::new(false);
+    let redirect_uri = RwSignal::new(String::new());
+    let post_login_url = RwSignal::new(String::new());
+    let scopes = RwSignal::new(String::new());
+    let groups_claim = RwSignal::new(String::new());
+    let default_role_code = RwSignal::new(String::new());
+    let auto_create_users = RwSignal::new(false);
+    let mappings = RwSignal::<Vec<OidcRoleMapping>>::new(Vec::new());
+
+    let apply = move |s: OidcSettings| {
+        enabled.set(s.enabled);
+        display_name.set(s.display_name);
+        issuer_url.set(s.issuer_url);
+        client_id.set(s.client_id);
+        client_secret.set(String::new());
+        client_secret_set.set(s.client_secret_set);
+        redirect_uri.set(s.redirect_uri);
+        post_login_url.set(s.post_login_url);
+        scopes.set(s.scopes);
+        groups_claim.set(s.groups_claim);
+        default_role_code.set(s.default_role_code.unwrap_or_default());
+        auto_create_users.set(s.auto_create_users);
+        mappings.set(s.role_mappings);
+    };
+
+    let reload = Callback::new(move |_| {
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match api::fetch_settings().await {
+                Ok(settings) => apply(settings),
+                Err(err) => error.set(Some(err)),
+            }
+            if let Ok(list) = fetch_roles().await {
+                roles.set(list);
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        reload.run(());
+    });
+
+    let save = move |_| {
+        let default_role = default_role_code.get_untracked();
+        let settings = OidcSettings {
+            enabled: enabled.get_untracked(),
+            display_name: display_name.get_untracked(),
+            issuer_url: issuer_url.get_untracked(),
+            client_id: client_id.get_untracked(),
+            client_secret: client_secret.get_untracked(),
+            client_secret_set: client_secret_set.get_untracked(),
+            redirect_uri: redirect_uri.get_untracked(),
+            post_login_url: post_login_url.get_untracked(),
+            scopes: scopes.get_untracked(),
+            groups_claim: groups_claim.get_untracked(),
+            role_mappings: mappings.get_untracked(),
+            default_role_code: (!default_role.is_empty()).then_some(default_role),
+            auto_create_users: auto_create_users.get_untracked(),
+        };
+        saving.set(true);
+        error.set(None);
+        notice.set(None);
+        spawn_local(async move {
+            match api::save_settings(&settings).await {
+                Ok(saved) => {
+                    apply(saved);
+                    notice.set(Some("Настройки входа через SSO сохранены".to_string()));
+                }
+                Err(err) => error.set(Some(err)),
+            }
+            saving.set(false);
+        });
+    };
+
+    let fill_redirect_uri = move |_| {
+        redirect_uri.set(format!("{}/api/system/auth/oidc/callback", api_base()));
+    };
+
+    let update_mapping = move |index: usize, f: &dyn Fn(&mut OidcRoleMapping)| {
+        mappings.update(|list| {
+            if let Some(mapping) = list.get_mut(index) {
+                f(mapping);
+            }
+        });
+    };
+
+    let role_options = move |selected: String| {
+        roles
+            .get()
+            .into_iter()
+            .map(|role| {
+                let is_selected = role.code == selected;
+                view! {
+                    <option value=role.code.clone() selected=is_selected>
+                        {format!("{} — {}", role.code, role.name)}
+                    </option>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <PageFrame page_id="sys_sso--system" category=PAGE_CAT_SYSTEM class="page--wide">
+            <div class="page__header">
+                <div class="page__header-left">
+                    <h1 class="page__title">"Вход через SSO"</h1>
+                    <p class="page__subtitle">"Корпоративный OIDC-провайдер (authorization code flow). Роль пользователя определяется группами из ID-токена при каждом входе; вход по паролю остаётся доступным."</p>
+                </div>
+                <div class="page__header-right">
+                    <button
+                        class="button button--secondary"
+                        disabled=move || loading.get()
+                        on:click=move |_| reload.run(())
+                    >
+                        {icon("refresh-cw")}
+                        {move || if loading.get() { "Обновление данных..." } else { "Обновить данные" }}
+                    </button>
+                    <button
+                        class="button button--primary"
+                        disabled=move || saving.get() || loading.get()
+                        on:click=save
+                    >
+                        {icon("save")} "Сохранить"
+                    </button>
+                </div>
+            </div>
+
+            <div class="page__content">
+                {move || error.get().map(|err| view! {
+                    <div class="alert alert--error">{err}</div>
+                })}
+                {move || notice.get().map(|msg| view! {
+                    <div class="alert alert--success">{msg}</div>
+                })}
+
+                <section class="raw-storage__section">
+                    <h2 class="raw-storage__section-title">"Провайдер"</h2>
+                    <div class="raw-storage__list">
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Вход через SSO"</span>
+                            <Checkbox checked=enabled label="Включён" />
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Подпись кнопки"</span>
+                            <Input value=display_name placeholder="Корпоративный вход" />
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Issuer URL"</span>
+                            <Input value=issuer_url placeholder="https://sso.example.com/realms/corp" />
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Client ID"</span>
+                            <Input value=client_id />
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Client secret"</span>
+                            <input
+                                class="form__input"
+                                type="password"
+                                autocomplete="new-password"
+                                placeholder=move || {
+                                    if client_secret_set.get() {
+                                        "Сохранён — оставьте пустым, чтобы не менять"
+                                    } else {
+                                        "Не задан"
+                                    }
+                                }
+                                prop:value=move || client_secret.get()
+                                on:input=move |ev| client_secret.set(event_target_value(&ev))
+                            />
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Redirect URI"</span>
+                            <div class="raw-storage__list-action-group">
+                                <Input value=redirect_uri placeholder="https://…/api/system/auth/oidc/callback" />
+                                <button class="button button--secondary" on:click=fill_redirect_uri>
+                                    "Текущий сервер"
+                                </button>
+                            </div>
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Адрес фронтенда после входа"</span>
+                            <Input value=post_login_url placeholder="Пусто — корень сервера из Redirect URI" />
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Scopes"</span>
+                            <Input value=scopes placeholder="openid profile email" />
+                        </div>
+                    </div>
+                </section>
+
+                <section class="raw-storage__section">
+                    <h2 class="raw-storage__section-title">"Группы и роли"</h2>
+                    <div class="raw-storage__list">
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Claim с группами"</span>
+                            <Input value=groups_claim placeholder="groups" />
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Без подходящей группы"</span>
+                            <select
+                                class="thaw-input"
+                                prop:value=move || default_role_code.get()
+                                on:change=move |ev| default_role_code.set(event_target_value(&ev))
+                            >
+                                <option value="">"— вход запрещён"</option>
+                                {move || role_options(default_role_code.get_untracked())}
+                            </select>
+                        </div>
+                        <div class="raw-storage__list-row">
+                            <span class="raw-storage__list-label">"Новые пользователи"</span>
+                            <Checkbox checked=auto_create_users label="Создавать при первом входе" />
+                        </div>
+                    </div>
+
+                    <div class="table-wrapper">
+                        <table class="table__data table--striped">
+                            <thead class="table__head">
+                                <tr>
+                                    <th class="table__header-cell">"Группа провайдера"</th>
+                                    <th class="table__header-cell">"Роль"</th>
+                                    <th class="table__header-cell">"Администратор"</th>
+                                    <th class="table__header-cell"></th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {move || {
+                                    mappings
+                                        .get()
+                                        .into_iter()
+                                        .enumerate()
+                                        .map(|(index, mapping)| {
+                                            let role_code = mapping.role_code.clone();
+                                            view! {
+                                                <tr class="table__row">
+                                                    <td class="table__cell">
+                                                        <input
+                                                            class="form__input"
+                                                            type="text"
+                                                            prop:value=mapping.group.clone()
+                                                            on:change=move |ev| {
+                                                                let value = event_target_value(&ev);
+                                                                update_mapping(index, &|m| m.group = value.clone());
+                                                            }
+                                                        />
+                                                    </td>
+                                                    <td class="table__cell">
+                                                        <select
+                                                            class="thaw-input"
+                                                            on:change=move |ev| {
+                                                                let value = event_target_value(&ev);
+                                                                update_mapping(index, &|m| m.role_code = value.clone());
+                                                            }
+                                                        >
+                                                            <option value="" selected=role_code.is_empty()>"— выберите роль"</option>
+                                                            {role_options(role_code.clone())}
+                                                        </select>
+                                                    </td>
+                                                    <td class="table__cell">
+                                                        <input
+                                                            type="checkbox"
+                                                            prop:checked=mapping.is_admin
+                                                            on:change=move |ev| {
+                                                                let checked = event_target_checked(&ev);
+                                                                update_mapping(index, &|m| m.is_admin = checked);
+                                                            }
+                                                        />
+                                                    </td>
+                                                    <td class="table__cell">
+                                                        <button
+                                                            class="button button--secondary"
+                                                            title="Удалить сопоставление"
+                                                            on:click=move |_| mappings.update(|list| {
+                                                                if index < list.len() {
+                                                                    list.remove(index);
+                                                                }
+                                                            })
+                                                        >
+                                                            {icon("trash")}
+                                                        </button>
+                                                    </td>
+                                                </tr>
+                                            }
+                                        })
+                                        .collect_view()
+                                }}
+                            </tbody>
+                        </table>
+                    </div>
+                    <p class="page__subtitle">"Основная роль — из первой совпавшей строки; права администратора — если совпала любая строка с отметкой."</p>
+                    <button
+                        class="button button--secondary"
+                        on:click=move |_| mappings.update(|list| list.push(OidcRoleMapping::default()))
+                    >
+                        {icon("plus")} "Добавить группу"
+                    </button>
+                </section>
+            </div>
+        </PageFrame>
+    }
+}
@@ -1,5 +1,7 @@
+use crate::shared::api_client::{self, ApiError};
 use crate::shared::api_utils::api_base;
 use crate::system::auth::storage;
+use contracts::shared::api_error::ApiErrorBody;
 use contracts::system::ext_api_log::{
     ExtApiHistoryResponse, ExtApiLogListResponse, ExtApiMetric, ExtApiScale, ExtApiSummaryResponse,
 };
@@ -7,13 +9,15 @@ use contracts::system::tasks::history::{TaskHistoryMetric, TaskHistoryResponse,
 use contracts::system::tasks::metadata::TaskMetadataDto;
 use contracts::system::tasks::progress::TaskProgressResponse;
 use contracts::system::tasks::request::{
-    CreateScheduledTaskDto, SchedulerStatusDto, SetWatermarkDto, ToggleScheduledTaskEnabledDto,
-    UpdateScheduledTaskDto,
+    CreateScheduledTaskDto, ImportSummarySubscriptionDto, SchedulerStatusDto, SetWatermarkDto,
+    ToggleScheduledTaskEnabledDto, UpdateScheduledTaskDto,
+};
+use contracts::system::tasks::response::{
+    ScheduleCronPreviewResponse, ScheduledTaskListResponse, ScheduledTaskResponse,
 };
-use contracts::system::tasks::response::{ScheduledTaskListResponse, ScheduledTaskResponse};
 use contracts::system::tasks::runs::{
-    LiveMemoryProgressResponse, RecentRunsResponse, RunTaskResponse, TaskRun, TaskRunListResponse,
-    TaskStartConflict,
+    ConcurrencyStatusResponse, LiveMemoryProgressResponse, RecentRunsResponse, RunTaskResponse,
+    TaskRun, TaskRunListResponse, TaskStartConflict,
 };
 use gloo_net::http::Request;
 use serde_json;
@@ -167,6 +171,26 @@ pub async fn get_scheduled_task(id: &str) -> Result<ScheduledTaskResponse, Strin
         .map_err(|e| format!("Failed to parse response: {}", e))
 }
 
+/// Текст ошибки из JSON-конверта `AppError` (например, некорректное расписание),
+/// иначе — HTTP-статус.
+async fn error_message(response: gloo_net::http::Response, what: &str) -> String {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    match serde_json::from_str::<ApiErrorBody>(&body) {
+        Ok(error) => error.message,
+        Err(_) => format!("Failed to {}: {}", what, status),
+    }
+}
+
+/// Проверка cron-выражения на сервере и ближайшие запуски.
+pub async fn preview_schedule(cron: &str) -> Result<ScheduleCronPreviewResponse, ApiError> {
+    api_client::get_json(&format!(
+        "/api/sys/tasks/schedule-preview?cron={}",
+        urlencoding::encode(cron)
+    ))
+    .await
+}
+
 /// Create new scheduled task
 pub async fn create_scheduled_task(
     dto: CreateScheduledTaskDto,
@@ -182,10 +206,7 @@ pub async fn create_scheduled_task(
         .map_err(|e| format!("Failed to send request: {}", e))?;
 
     if !response.ok() {
-        return Err(format!(
-            "Failed to create scheduled task: {}",
-            response.status()
-        ));
+        return Err(error_message(response, "create scheduled task").await);
     }
 
     response
@@ -210,10 +231,7 @@ pub async fn update_scheduled_task(
         .map_err(|e| format!("Failed to send request: {}", e))?;
 
     if !response.ok() {
-        return Err(format!(
-            "Failed to update scheduled task: {}",
-            response.status()
-        ));
+        return Err(error_message(response, "update scheduled task").await);
     }
 
     response
@@ -385,6 +403,15 @@ pub async fn get_active_runs_with_progress() -> Result<LiveMemoryProgressRespons
         .map_err(|e| format!("Failed to parse response: {}", e))
 }
 
+/// Занятые слоты классов параллельности (только память координатора).
+pub async fn get_concurrency_slots() -> Result<ConcurrencyStatusResponse, String> {
+    get_json(
+        format!("{}/api/sys/tasks/runs/active/slots", api_base()),
+        "concurrency slots",
+    )
+    .await
+}
+
 /// Get run history for a specific task
 pub async fn get_task_runs(
     task_id: &str,
@@ -629,3 +656,63 @@ pub async fn set_scheduler_status(enabled: bool) -> Result<SchedulerStatusDto, S
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))
 }
+
+/// Подписка текущего администратора на письмо-сводку плановых импортов.
+pub async fn fetch_import_summary_subscription() -> Result<ImportSummarySubscriptionDto, String> {
+    let auth_header = get_auth_header().ok_or("Not authenticated")?;
+
+    let response = Request::get(&format!(
+        "{}/api/sys/tasks/import-summary/subscription",
+        api_base()
+    ))
+    .header("Authorization", &auth_header)
+    .send()
+    .await
+    .map_err(|e| format!("Request failed: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Import summary subscription request failed: {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+/// Подписаться на сводку плановых импортов или отписаться.
+pub async fn set_import_summary_subscription(
+    subscribed: bool,
+) -> Result<ImportSummarySubscriptionDto, String> {
+    let auth_header = get_auth_header().ok_or("Not authenticated")?;
+
+    let dto = ImportSummarySubscriptionDto {
+        subscribed,
+        email: None,
+    };
+    let response = Request::post(&format!(
+        "{}/api/sys/tasks/import-summary/subscription",
+        api_base()
+    ))
+    .header("Authorization", &auth_header)
+    .json(&dto)
+    .map_err(|e| format!("Failed to serialize: {}", e))?
+    .send()
+    .await
+    .map_err(|e| format!("Request failed: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Set import summary subscription failed: {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
@@ -1,4 +1,6 @@
+use crate::shared::api_client::ApiError;
 use crate::shared::api_utils::api_base;
+use crate::shared::date_utils::format_utc_local;
 use crate::system::auth::storage;
 use contracts::system::tasks::metadata::{TaskConfigFieldDto, TaskConfigFieldTypeDto};
 use gloo_net::http::Request;
@@ -22,8 +24,37 @@ const CRON_PRESETS: &[(&str, &str)] = &[
 ];
 
 /// Visual cron editor with preset buttons and a free-form input.
+/// Below the input the server-side check shows the next runs or the parse error
+/// (5-field expressions are accepted and get a `0` seconds field on save).
 #[component]
 pub fn CronEditor(value: RwSignal<String>) -> impl IntoView {
+    let (preview, set_preview) = signal(None::<Result<Vec<String>, String>>);
+    // Номер последнего запроса: ответы на устаревшие выражения отбрасываются.
+    let request_seq = StoredValue::new(0u32);
+    Effect::new(move |_| {
+        let cron = value.get();
+        let seq = request_seq.get_value() + 1;
+        request_seq.set_value(seq);
+        if cron.trim().is_empty() {
+            set_preview.set(None);
+            return;
+        }
+        spawn_local(async move {
+            let result = match crate::system::tasks::api::preview_schedule(&cron).await {
+                Ok(preview) => Ok(preview
+                    .next_runs
+                    .iter()
+                    .map(|dt| format_utc_local(dt, "%d.%m.%Y %H:%M:%S"))
+                    .collect()),
+                Err(ApiError::Server { error, .. }) => Err(error.message),
+                Err(e) => Err(e.to_string()),
+            };
+            if request_seq.get_value() == seq {
+                set_preview.set(Some(result));
+            }
+        });
+    });
+
     view! {
         <div>
             <div style="display:flex;flex-wrap:wrap;gap:6px;margin-bottom:8px;">
@@ -58,8 +89,23 @@ pub fn CronEditor(value: RwSignal<String>) -> impl IntoView {
             </div>
             <Input value placeholder="0 */5 * * * *" />
             <div style="font-size:11px;color:var(--color-text-tertiary);margin-top:4px;">
-                "Формат: сек мин час день месяц день_нед  •  Пример: «0 0 2 * * *» — каждый день в 2:00"
+                "Формат: [сек] мин час день месяц день_нед  •  Пример: «0 0 2 * * *» — каждый день в 2:00"
             </div>
+            {move || match preview.get() {
+                None => view! { <span></span> }.into_any(),
+                Some(Ok(runs)) => view! {
+                    <div style="font-size:11px;color:var(--color-text-secondary);margin-top:4px;">
+                        {format!("Ближайшие запуски: {}", runs.join(", "))}
+                    </div>
+                }
+                .into_any(),
+                Some(Err(message)) => view! {
+                    <div style="font-size:11px;color:var(--color-error);margin-top:4px;">
+                        {message}
+                    </div>
+                }
+                .into_any(),
+            }}
         </div>
     }
 }
@@ -1,6 +1,9 @@
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::components::card_animated::CardAnimated;
-use crate::shared::date_utils::{format_duration_ms, format_http_traffic, format_utc_local};
+use crate::shared::components::import_provenance::ImportProvenanceSection;
+use crate::shared::date_utils::{
+    format_duration_ms, format_http_calls, format_http_traffic, format_utc_local,
+};
 use crate::shared::icons::icon;
 use crate::shared::page_frame::PageFrame;
 use crate::system::tasks::api;
@@ -17,10 +20,10 @@ use leptos::task::spawn_local;
 use thaw::*;
 
 fn run_history_http_calls(run: &TaskRun) -> String {
-    run.http_request_count
-        .filter(|&n| n > 0)
-        .map(|n| n.to_string())
-        .unwrap_or_else(|| "—".to_string())
+    format_http_calls(
+        run.http_request_count.unwrap_or(0),
+        run.http_retry_count.unwrap_or(0),
+    )
 }
 
 fn run_history_http_traffic(run: &TaskRun) -> String {
@@ -171,17 +174,22 @@ fn RunHistoryRow(run: TaskRun) -> impl IntoView {
     }
 }
 
+/// Карточка задачи. `run_session_id` — открыть лог конкретного запуска
+/// (deep-link из письма-сводки: вкладка `sys_task_run_{id}_{session_id}`).
 #[component]
-pub fn ScheduledTaskDetails(id: String) -> impl IntoView {
+pub fn ScheduledTaskDetails(
+    id: String,
+    #[prop(optional)] run_session_id: Option<String>,
+) -> impl IntoView {
     let is_new = id == "new";
 
     let tabs_store =
         leptos::context::use_context::<AppGlobalContext>().expect("AppGlobalContext not found");
 
-    let tab_key = if is_new {
-        "sys_task_details".to_string()
-    } else {
-        format!("sys_task_details_{}", id)
+    let tab_key = match (&run_session_id, is_new) {
+        (Some(sid), _) => format!("sys_task_run_{}_{}", id, sid),
+        (None, true) => "sys_task_details".to_string(),
+        (None, false) => format!("sys_task_details_{}", id),
     };
 
     let close_tab = {
@@ -212,11 +220,17 @@ pub fn ScheduledTaskDetails(id: String) -> impl IntoView {
     let session_id = RwSignal::new(None::<String>);
     let run_progress = RwSignal::new(None::<TaskProgressResponse>);
     let log_content = RwSignal::new(String::new());
+    // Сессия, лог которой показан, — для раздела «Происхождение импорта»
+    let log_session_id = RwSignal::new(None::<String>);
 
     let (runs, set_runs) = signal(Vec::<TaskRun>::new());
     let (runs_loading, set_runs_loading) = signal(false);
     let (metadata, set_metadata) = signal(None::<TaskMetadataDto>);
-    let (active_tab, set_active_tab) = signal("settings".to_string());
+    let (active_tab, set_active_tab) = signal(if run_session_id.is_some() {
+        "logs".to_string()
+    } else {
+        "settings".to_string()
+    });
     let (task_types, set_task_types) = signal(Vec::<TaskMetadataDto>::new());
 
     // ---- Load available task types once ----
@@ -308,7 +322,7 @@ pub fn ScheduledTaskDetails(id: String) -> impl IntoView {
         }
 
         let tid = logs_task_id.clone();
-        let sid = session_id.get();
+        let sid = session_id.get().or_else(|| run_session_id.clone());
         spawn_local(async move {
             let sid = match sid {
                 Some(sid) => sid,
@@ -327,6 +341,7 @@ pub fn ScheduledTaskDetails(id: String) -> impl IntoView {
                 },
             };
 
+            log_session_id.set(Some(sid.clone()));
             match api::get_task_log(&tid, &sid).await {
                 Ok(log) => log_content.set(log),
                 Err(e) => log_content.set(format!("Не удалось загрузить лог: {}", e)),
@@ -909,6 +924,9 @@ pub fn ScheduledTaskDetails(id: String) -> impl IntoView {
                                 if c.is_empty() { "Нет данных лога. Запустите задачу чтобы увидеть лог.".to_string() } else { c }
                             }}
                         </div>
+                        {move || log_session_id.get().map(|sid| view! {
+                            <ImportProvenanceSection session_id=sid />
+                        })}
                     </div>
                 </div>
             </div>
@@ -989,6 +1007,16 @@ pub fn ScheduledTaskDetails(id: String) -> impl IntoView {
                                         <div style="font-size:var(--font-size-sm);font-weight:700;letter-spacing:0.04em;text-transform:uppercase;color:var(--color-text-tertiary);margin-bottom:6px;">"Описание"</div>
                                         <div style="font-size:var(--font-size-base);color:var(--color-text);line-height:1.6;">{m.description.clone()}</div>
                                     </div>
+                                    <div>
+                                        <div style="font-size:var(--font-size-sm);font-weight:700;letter-spacing:0.04em;text-transform:uppercase;color:var(--color-text-tertiary);margin-bottom:6px;">"Класс параллельности"</div>
+                                        <div style="font-size:var(--font-size-base);color:var(--color-text);">
+                                            {format!(
+                                                "{} — не более {} одновременно",
+                                                m.concurrency_class.label(),
+                                                m.concurrency_class.max_concurrent()
+                                            )}
+                                        </div>
+                                    </div>
                                     {if !m.write_tables.is_empty() { view! {
                                         <div>
                                             <div style="font-size:var(--font-size-sm);font-weight:700;letter-spacing:0.04em;text-transform:uppercase;color:var(--color-text-tertiary);margin-bottom:8px;">"Таблицы записи"</div>
@@ -0,0 +1,64 @@
+//! Панель «Слоты выполнения» на вкладке «Активные»: загрузка классов параллельности
+//! воркера (сколько задач класса выполняется из допустимых) и кто занимает слоты.
+
+use super::elapsed_from;
+use contracts::system::tasks::runs::ConcurrencyClassStatus;
+use leptos::prelude::*;
+
+#[component]
+pub fn ConcurrencySlotsPanel(classes: ReadSignal<Vec<ConcurrencyClassStatus>>) -> impl IntoView {
+    view! {
+        <div style="display:grid;grid-template-columns:repeat(auto-fill,minmax(220px,1fr));gap:8px;">
+            {move || {
+                classes
+                    .get()
+                    .into_iter()
+                    .map(|status| {
+                        let used = status.running.len();
+                        let full = used >= status.limit;
+                        let border = if full {
+                            "var(--colorPaletteDarkOrangeBorder2)"
+                        } else {
+                            "var(--color-border)"
+                        };
+                        view! {
+                            <div style=format!(
+                                "padding:8px 10px;border:1px solid {};border-radius:var(--radius-md);font-size:12px;",
+                                border,
+                            )>
+                                <div style="display:flex;justify-content:space-between;gap:8px;font-weight:600;">
+                                    <span>{status.label.clone()}</span>
+                                    <span
+                                        style="font-family:monospace;"
+                                        title="Выполняется / максимум одновременно"
+                                    >
+                                        {format!("{} / {}", used, status.limit)}
+                                    </span>
+                                </div>
+                                {status
+                                    .running
+                                    .into_iter()
+                                    .map(|slot| {
+                                        view! {
+                                            <div
+                                                style="display:flex;justify-content:space-between;gap:8px;margin-top:4px;color:var(--color-text-secondary);"
+                                                title=slot.session_id.clone()
+                                            >
+                                                <span style="overflow:hidden;text-overflow:ellipsis;white-space:nowrap;">
+                                                    {slot.task_label.clone()}
+                                                </span>
+                                                <span style="font-family:monospace;">
+                                                    {elapsed_from(&slot.started_at)}
+                                                </span>
+                                            </div>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </div>
+                        }
+                    })
+                    .collect_view()
+            }}
+        </div>
+    }
+}
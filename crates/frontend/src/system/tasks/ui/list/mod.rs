@@ -1,10 +1,12 @@
+mod concurrency;
 pub mod state;
 
+use self::concurrency::ConcurrencySlotsPanel;
 use self::state::create_state;
 use crate::layout::global_context::AppGlobalContext;
 use crate::shared::change_tokens::ChangeTokenContext;
 use crate::shared::date_utils::{
-    format_duration_ms, format_http_traffic, format_utc_local, TZ_OFFSET_HOURS,
+    format_duration_ms, format_http_calls, format_http_traffic, format_utc_local, TZ_OFFSET_HOURS,
 };
 use crate::shared::icons::icon;
 use crate::shared::list_utils::{get_sort_class, get_sort_indicator, sort_list, Sortable};
@@ -14,7 +16,7 @@ use crate::system::tasks::ui::history::TaskHistoryView;
 use chrono::Utc;
 use contracts::system::tasks::progress::{task_progress_detail_caption_ru, TaskProgressResponse};
 use contracts::system::tasks::response::ScheduledTaskResponse;
-use contracts::system::tasks::runs::{LiveMemoryProgressItem, TaskRun};
+use contracts::system::tasks::runs::{ConcurrencyClassStatus, LiveMemoryProgressItem, TaskRun};
 use gloo_timers::future::TimeoutFuture;
 use leptos::ev::MouseEvent;
 use leptos::logging::log;
@@ -36,12 +38,10 @@ fn elapsed_from(started_at: &chrono::DateTime<chrono::Utc>) -> String {
 
 /// Активная статистика HTTP для строки «Активные задачи» (WB и др.).
 fn live_http_calls(p: &TaskProgressResponse) -> String {
-    let n = p.http_request_count.unwrap_or(0);
-    if n == 0 {
-        "—".to_string()
-    } else {
-        n.to_string()
-    }
+    format_http_calls(
+        p.http_request_count.unwrap_or(0) as i64,
+        p.http_retry_count.unwrap_or(0) as i64,
+    )
 }
 
 fn live_http_traffic(p: &TaskProgressResponse) -> String {
@@ -53,10 +53,10 @@ fn live_http_traffic(p: &TaskProgressResponse) -> String {
 }
 
 fn monitoring_http_calls(run: &TaskRun) -> String {
-    run.http_request_count
-        .filter(|&n| n > 0)
-        .map(|n| n.to_string())
-        .unwrap_or_else(|| "—".to_string())
+    format_http_calls(
+        run.http_request_count.unwrap_or(0),
+        run.http_retry_count.unwrap_or(0),
+    )
 }
 
 fn monitoring_http_traffic(run: &TaskRun) -> String {
@@ -220,6 +220,7 @@ impl Sortable for ScheduledTaskResponse {
                 .to_lowercase()
                 .cmp(&other.task_type.to_lowercase()),
             "last_run_at" => self.last_run_at.cmp(&other.last_run_at),
+            "next_run_at" => self.next_run_at.cmp(&other.next_run_at),
             "last_run_status" => self
                 .last_run_status
                 .as_deref()
@@ -497,6 +498,13 @@ pub fn ScheduledTaskList() -> impl IntoView {
     // Включён ли планировщик в config.toml ([scheduled_tasks].enabled).
     // Если false — фоновый воркер не запущен, переключатель не имеет эффекта.
     let scheduler_config_enabled = RwSignal::new(true);
+    // Подписка текущего администратора на письмо-сводку плановых импортов.
+    // `summary_saved` — значение на сервере: Effect ниже шлёт POST только при расхождении.
+    let summary_subscribed = RwSignal::new(false);
+    let summary_saved = StoredValue::new(None::<bool>);
+    let summary_email = RwSignal::new(None::<String>);
+    // Эндпоинт только для администраторов: остальным переключатель не показываем.
+    let summary_available = RwSignal::new(false);
 
     // Дата последнего действия со планировщиком (сохраняется в localStorage)
     const LS_KEY: &str = "sys_tasks_scheduler_last_action";
@@ -587,6 +595,7 @@ pub fn ScheduledTaskList() -> impl IntoView {
     // Monitoring state
     let (recent_runs, set_recent_runs) = signal(Vec::<TaskRun>::new());
     let (live_memory_items, set_live_memory_items) = signal(Vec::<LiveMemoryProgressItem>::new());
+    let (concurrency_slots, set_concurrency_slots) = signal(Vec::<ConcurrencyClassStatus>::new());
     let (runs_loading, set_runs_loading) = signal(false);
     let (batch_warning, set_batch_warning) = signal::<Option<String>>(None);
 
@@ -661,6 +670,10 @@ pub fn ScheduledTaskList() -> impl IntoView {
                 Ok(resp) => set_live_memory_items.set(resp.items),
                 Err(e) => log!("Failed to load live memory progress: {}", e),
             }
+            match api::get_concurrency_slots().await {
+                Ok(resp) => set_concurrency_slots.set(resp.classes),
+                Err(e) => log!("Failed to load concurrency slots: {}", e),
+            }
             if show_spinner {
                 set_runs_loading.set(false);
             }
@@ -719,6 +732,32 @@ pub fn ScheduledTaskList() -> impl IntoView {
         enabled
     });
 
+    spawn_local(async move {
+        if let Ok(sub) = api::fetch_import_summary_subscription().await {
+            summary_saved.set_value(Some(sub.subscribed));
+            summary_email.set(sub.email);
+            summary_subscribed.set(sub.subscribed);
+            summary_available.set(true);
+        }
+    });
+
+    Effect::new(move |_| {
+        let subscribed = summary_subscribed.get();
+        let Some(saved) = summary_saved.get_value() else {
+            return;
+        };
+        if saved == subscribed {
+            return;
+        }
+        summary_saved.set_value(Some(subscribed));
+        spawn_local(async move {
+            match api::set_import_summary_subscription(subscribed).await {
+                Ok(sub) => summary_email.set(sub.email),
+                Err(e) => log!("Failed to set import summary subscription: {}", e),
+            }
+        });
+    });
+
     // Автообновление списка «Задания» при изменении токена на сервере.
     // prev=None при первом вызове — пропускаем, чтобы не делать двойную загрузку.
     let ct = use_context::<ChangeTokenContext>().expect("ChangeTokenContext not found");
@@ -749,6 +788,12 @@ pub fn ScheduledTaskList() -> impl IntoView {
                 Ok(resp) => set_live_memory_items.set(resp.items),
                 Err(e) => log!("Live memory background poll failed: {}", e),
             }
+            // Слоты нужны только на открытой вкладке «Активные»
+            if active_tab.try_get_untracked().as_deref() == Some("active") {
+                if let Ok(resp) = api::get_concurrency_slots().await {
+                    set_concurrency_slots.set(resp.classes);
+                }
+            }
         }
     });
 
@@ -1040,6 +1085,18 @@ pub fn ScheduledTaskList() -> impl IntoView {
                                         {ts}
                                     </span>
                                 })}
+                                <Show when=move || summary_available.get()>
+                                <div
+                                    style="display:flex;align-items:center;gap:8px;margin-left:16px;"
+                                    title=move || match summary_email.get() {
+                                        Some(email) => format!("Письмо-сводка после каждого планового импорта на {email}"),
+                                        None => "В профиле не указан email — письма не придут".to_string(),
+                                    }
+                                >
+                                    <Switch checked=summary_subscribed />
+                                    <span style="font-size:13px;user-select:none;">"Сводка импортов на почту"</span>
+                                </div>
+                                </Show>
                             </Flex>
                         </Flex>
 
@@ -1066,6 +1123,8 @@ pub fn ScheduledTaskList() -> impl IntoView {
                                     <TableHeaderCell resizable=true min_width=130.0>"Расписание"</TableHeaderCell>
                                     <SortHeaderCell label="Последний запуск" field="last_run_at" min_width=120.0
                                         sort_field=sort_field sort_ascending=sort_ascending on_toggle=toggle_sort />
+                                    <SortHeaderCell label="Следующий запуск" field="next_run_at" min_width=120.0
+                                        sort_field=sort_field sort_ascending=sort_ascending on_toggle=toggle_sort />
                                     <SortHeaderCell label="Статус" field="last_run_status" min_width=100.0
                                         sort_field sort_ascending on_toggle=toggle_sort />
                                     <SortHeaderCell label="Авто" field="is_enabled" min_width=70.0
@@ -1078,7 +1137,7 @@ pub fn ScheduledTaskList() -> impl IntoView {
                                     if loading.get() {
                                         view! {
                                             <TableRow>
-                                                <TableCell attr:colspan="8" attr:style="padding: 40px; text-align: center;">
+                                                <TableCell attr:colspan="9" attr:style="padding: 40px; text-align: center;">
                                                     <Flex justify=FlexJustify::Center align=FlexAlign::Center gap=FlexGap::Small>
                                                         <Spinner />
                                                         "Загрузка..."
@@ -1091,7 +1150,7 @@ pub fn ScheduledTaskList() -> impl IntoView {
                                         if tasks.is_empty() {
                                             view! {
                                                 <TableRow>
-                                                    <TableCell attr:colspan="8" attr:style="padding: 40px; text-align: center; color: var(--colorNeutralForeground3);">
+                                                    <TableCell attr:colspan="9" attr:style="padding: 40px; text-align: center; color: var(--colorNeutralForeground3);">
                                                         "Заданий не найдено"
                                                     </TableCell>
                                                 </TableRow>
@@ -1218,6 +1277,16 @@ pub fn ScheduledTaskList() -> impl IntoView {
                                                             </span>
                                                         </TableCell>
 
+                                                        // Следующий запуск (только для включённых задач)
+                                                        <TableCell>
+                                                            <span style="font-size:0.9em;color:var(--colorNeutralForeground2);">
+                                                                {task.next_run_at
+                                                                    .filter(|_| is_enabled)
+                                                                    .map(|d| format_utc_local(&d, "%d.%m %H:%M"))
+                                                                    .unwrap_or_else(|| "—".to_string())}
+                                                            </span>
+                                                        </TableCell>
+
                                                         // Статус
                                                         <TableCell><span class=status_class>{status_label}</span></TableCell>
 
@@ -1255,6 +1324,9 @@ pub fn ScheduledTaskList() -> impl IntoView {
                         <MessageBar intent=MessageBarIntent::Info>
                                "Все серверные задачи. Обновляется каждые 2 с, пока открыта вкладка. Показаны сессии «Running» в трекерах. История во вкладке «Мониторинг»"
                         </MessageBar>
+                        <div style="margin:8px 0;">
+                            <ConcurrencySlotsPanel classes=concurrency_slots />
+                        </div>
                         {move || if runs_loading.get() {
                             view! {
                                 <Flex justify=FlexJustify::Center align=FlexAlign::Center style="padding:32px;">
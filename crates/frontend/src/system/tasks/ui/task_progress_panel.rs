@@ -96,11 +96,17 @@ pub fn TaskProgressPanel(
         let n = progress.http_request_count.unwrap_or(0);
         let up = progress.http_bytes_sent.unwrap_or(0).max(0) as u64;
         let down = progress.http_bytes_received.unwrap_or(0).max(0) as u64;
-        if n == 0 && up == 0 && down == 0 {
+        let retries = progress.http_retry_count.unwrap_or(0);
+        if n == 0 && up == 0 && down == 0 && retries == 0 {
             None
         } else {
+            let retries = if retries > 0 {
+                format!(" · повторов: {retries}")
+            } else {
+                String::new()
+            };
             Some(format!(
-                "HTTP: {n} · ↑{} ↓{}",
+                "HTTP: {n} · ↑{} ↓{}{retries}",
                 format_bytes_compact(up),
                 format_bytes_compact(down)
             ))
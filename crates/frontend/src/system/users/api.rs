@@ -1,4 +1,8 @@
-use contracts::system::users::{ChangePasswordDto, CreateUserDto, UpdateUserDto, User};
+use contracts::system::org_context::UserOrganizationsDto;
+use contracts::system::roles::UserRolesDto;
+use contracts::system::users::{
+    ChangePasswordDto, CreateUserDto, ExpiringUserDto, UpdateUserDto, User,
+};
 use gloo_net::http::Request;
 
 use crate::shared::api_utils::api_base;
@@ -29,6 +33,34 @@ pub async fn fetch_users() -> Result<Vec<User>, String> {
         .map_err(|e| format!("Failed to parse response: {}", e))
 }
 
+/// Учётные записи, срок доступа которых истекает в ближайшие `days` дней или уже истёк
+pub async fn fetch_expiring(days: i64) -> Result<Vec<ExpiringUserDto>, String> {
+    let auth_header = get_auth_header().ok_or("Not authenticated")?;
+
+    let response = Request::get(&format!(
+        "{}/api/system/users/expiring?days={}",
+        api_base(),
+        days
+    ))
+    .header("Authorization", &auth_header)
+    .header("Cache-Control", "no-cache")
+    .send()
+    .await
+    .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch expiring users: {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json::<Vec<ExpiringUserDto>>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
 /// Get user by ID
 pub async fn get_user(id: &str) -> Result<User, String> {
     let auth_header = get_auth_header().ok_or("Not authenticated")?;
@@ -132,3 +164,111 @@ pub async fn change_password(dto: ChangePasswordDto) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Fetch additional roles assigned to a user
+pub async fn get_user_roles(user_id: &str) -> Result<UserRolesDto, String> {
+    let auth_header = get_auth_header().ok_or("Not authenticated")?;
+
+    let response = Request::get(&format!(
+        "{}/api/system/users/{}/roles",
+        api_base(),
+        user_id
+    ))
+    .header("Authorization", &auth_header)
+    .header("Cache-Control", "no-cache")
+    .send()
+    .await
+    .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!("Failed to fetch user roles: {}", response.status()));
+    }
+
+    response
+        .json::<UserRolesDto>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+/// Replace additional roles of a user
+pub async fn update_user_roles(user_id: &str, dto: UserRolesDto) -> Result<(), String> {
+    let auth_header = get_auth_header().ok_or("Not authenticated")?;
+
+    let response = Request::put(&format!(
+        "{}/api/system/users/{}/roles",
+        api_base(),
+        user_id
+    ))
+    .header("Authorization", &auth_header)
+    .json(&dto)
+    .map_err(|e| format!("Failed to serialize request: {}", e))?
+    .send()
+    .await
+    .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to update user roles: {}",
+            response.status()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Fetch organizations available to a user
+pub async fn get_user_organizations(user_id: &str) -> Result<UserOrganizationsDto, String> {
+    let auth_header = get_auth_header().ok_or("Not authenticated")?;
+
+    let response = Request::get(&format!(
+        "{}/api/system/users/{}/organizations",
+        api_base(),
+        user_id
+    ))
+    .header("Authorization", &auth_header)
+    .header("Cache-Control", "no-cache")
+    .send()
+    .await
+    .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to fetch user organizations: {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json::<UserOrganizationsDto>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+/// Replace organizations available to a user
+pub async fn update_user_organizations(
+    user_id: &str,
+    dto: UserOrganizationsDto,
+) -> Result<(), String> {
+    let auth_header = get_auth_header().ok_or("Not authenticated")?;
+
+    let response = Request::put(&format!(
+        "{}/api/system/users/{}/organizations",
+        api_base(),
+        user_id
+    ))
+    .header("Authorization", &auth_header)
+    .json(&dto)
+    .map_err(|e| format!("Failed to serialize request: {}", e))?
+    .send()
+    .await
+    .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!(
+            "Failed to update user organizations: {}",
+            response.status()
+        ));
+    }
+
+    Ok(())
+}
@@ -1,13 +1,18 @@
+use contracts::system::org_context::{OrganizationOptionDto, UserOrganizationsDto};
+use contracts::system::roles::{Role, UserRolesDto};
 use contracts::system::users::{ChangePasswordDto, CreateUserDto, UpdateUserDto};
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use thaw::*;
 
 use crate::shared::components::card_animated::CardAnimated;
+use crate::shared::components::date_input::DateInput;
 use crate::shared::icons::icon;
 use crate::shared::page_frame::PageFrame;
 use crate::shared::page_standard::PAGE_CAT_SYSTEM;
 use crate::system::auth::guard::RequireAdmin;
+use crate::system::org_context::api as org_context_api;
+use crate::system::roles::api as roles_api;
 use crate::system::users::api;
 
 #[component]
@@ -34,8 +39,16 @@ fn UserDetailsInner(user_id: String) -> impl IntoView {
     let email = RwSignal::new(String::new());
     let full_name = RwSignal::new(String::new());
     let is_active = RwSignal::new(true);
+    let active_from = RwSignal::new(String::new());
+    let active_until = RwSignal::new(String::new());
     let is_admin = RwSignal::new(false);
     let primary_role_code = RwSignal::new("viewer".to_string());
+    // Дополнительные (пользовательские) роли: права складываются с основной ролью
+    let custom_roles = RwSignal::new(Vec::<Role>::new());
+    let assigned_role_ids = RwSignal::new(Vec::<String>::new());
+    // Организации, данные которых доступны пользователю (для администратора — все)
+    let organizations = RwSignal::new(Vec::<OrganizationOptionDto>::new());
+    let member_organization_ids = RwSignal::new(Vec::<String>::new());
 
     let new_password = RwSignal::new(String::new());
     let confirm_password = RwSignal::new(String::new());
@@ -51,11 +64,35 @@ fn UserDetailsInner(user_id: String) -> impl IntoView {
                     email.set(user.email.unwrap_or_default());
                     full_name.set(user.full_name.unwrap_or_default());
                     is_active.set(user.is_active);
+                    active_from.set(user.active_from.unwrap_or_default());
+                    active_until.set(user.active_until.unwrap_or_default());
                     is_admin.set(user.is_admin);
                     primary_role_code.set(user.primary_role_code);
                 }
                 Err(e) => set_error.set(Some(format!("Не удалось загрузить пользователя: {}", e))),
             }
+            match roles_api::fetch_roles().await {
+                Ok(roles) => custom_roles.set(roles.into_iter().filter(|r| !r.is_system).collect()),
+                Err(e) => set_error.set(Some(format!("Не удалось загрузить роли: {}", e))),
+            }
+            match api::get_user_roles(&uid).await {
+                Ok(dto) => assigned_role_ids.set(dto.role_ids),
+                Err(e) => set_error.set(Some(format!(
+                    "Не удалось загрузить роли пользователя: {}",
+                    e
+                ))),
+            }
+            match org_context_api::get_active().await {
+                Ok(dto) => organizations.set(dto.organizations),
+                Err(e) => set_error.set(Some(format!("Не удалось загрузить организации: {}", e))),
+            }
+            match api::get_user_organizations(&uid).await {
+                Ok(dto) => member_organization_ids.set(dto.organization_ids),
+                Err(e) => set_error.set(Some(format!(
+                    "Не удалось загрузить организации пользователя: {}",
+                    e
+                ))),
+            }
             set_loading.set(false);
         });
     });
@@ -87,9 +124,25 @@ fn UserDetailsInner(user_id: String) -> impl IntoView {
             is_active: is_active.get(),
             is_admin: is_admin.get(),
             primary_role_code: primary_role_code.get(),
+            active_from: Some(active_from.get()).filter(|v| !v.is_empty()),
+            active_until: Some(active_until.get()).filter(|v| !v.is_empty()),
+        };
+        let roles_dto = UserRolesDto {
+            role_ids: assigned_role_ids.get(),
+        };
+        let organizations_dto = UserOrganizationsDto {
+            organization_ids: member_organization_ids.get(),
         };
         spawn_local(async move {
-            match api::update_user(dto).await {
+            let result = match api::update_user(dto).await {
+                Ok(_) => api::update_user_roles(&uid, roles_dto).await,
+                Err(e) => Err(e),
+            };
+            let result = match result {
+                Ok(_) => api::update_user_organizations(&uid, organizations_dto).await,
+                Err(e) => Err(e),
+            };
+            match result {
                 Ok(_) => set_success.set(Some("Изменения сохранены".to_string())),
                 Err(e) => set_error.set(Some(format!("Ошибка сохранения: {}", e))),
             }
@@ -201,17 +254,122 @@ fn UserDetailsInner(user_id: String) -> impl IntoView {
                                 disabled=move || saving.get() || loading.get()
                             >
                                 <option value="viewer">"viewer — только просмотр"</option>
+                                <option value="warehouse">"warehouse — склад, без сумм"</option>
                                 <option value="operator">"operator — операционная работа"</option>
                                 <option value="manager">"manager — полный доступ"</option>
                                 <option value="admin">"admin — администратор системы"</option>
                             </select>
                         </div>
 
+                        <div class="form__group">
+                            <label class="form__label">"Срок доступа"</label>
+                            <div style="display: flex; gap: var(--spacing-sm); align-items: center;">
+                                "с"
+                                <DateInput
+                                    value=active_from
+                                    on_change=move |v: String| active_from.set(v)
+                                />
+                                "по"
+                                <DateInput
+                                    value=active_until
+                                    on_change=move |v: String| active_until.set(v)
+                                />
+                            </div>
+                            <p style="margin: var(--spacing-xs) 0 0; color: var(--color-text-secondary); font-size: 13px;">
+                                "Пусто — без ограничения. После даты окончания учётная запись отключается автоматически."
+                            </p>
+                        </div>
+
                         <div style="display: flex; gap: var(--spacing-lg); margin-top: var(--spacing-sm);">
                             <Checkbox checked=is_active label="Активен" />
                             <Checkbox checked=is_admin label="Суперадмин (is_admin bypass)" />
                         </div>
                     </CardAnimated>
+
+                    <CardAnimated delay_ms=40 nav_id="sys_user_details_roles">
+                        <h4 class="details-section__title">"Дополнительные роли"</h4>
+                        <p style="margin: 0 0 var(--spacing-sm); color: var(--color-text-secondary); font-size: 13px;">
+                            "Права дополнительных ролей добавляются к правам основной роли. Роли и их права настраиваются в разделе «Роли»."
+                        </p>
+                        {move || {
+                            let roles = custom_roles.get();
+                            if roles.is_empty() {
+                                view! {
+                                    <div style="color: var(--color-text-secondary); font-size: 13px;">
+                                        "Пользовательских ролей нет"
+                                    </div>
+                                }
+                                .into_any()
+                            } else {
+                                roles
+                                    .into_iter()
+                                    .map(|role| {
+                                        let role_id = role.id.clone();
+                                        let role_id_toggle = role.id.clone();
+                                        view! {
+                                            <label style="display: flex; gap: var(--spacing-sm); align-items: center; padding: 4px 0;">
+                                                <input
+                                                    type="checkbox"
+                                                    prop:checked=move || assigned_role_ids.with(|ids| ids.contains(&role_id))
+                                                    on:change=move |ev| {
+                                                        let checked = event_target_checked(&ev);
+                                                        let id = role_id_toggle.clone();
+                                                        assigned_role_ids.update(|ids| {
+                                                            ids.retain(|existing| existing != &id);
+                                                            if checked {
+                                                                ids.push(id);
+                                                            }
+                                                        });
+                                                    }
+                                                    disabled=move || saving.get() || loading.get()
+                                                />
+                                                <span>{role.name}</span>
+                                                <span style="color: var(--color-text-secondary); font-size: 12px;">{role.code}</span>
+                                            </label>
+                                        }
+                                    })
+                                    .collect_view()
+                                    .into_any()
+                            }
+                        }}
+                    </CardAnimated>
+
+                    <CardAnimated delay_ms=60 nav_id="sys_user_details_organizations">
+                        <h4 class="details-section__title">"Организации"</h4>
+                        <p style="margin: 0 0 var(--spacing-sm); color: var(--color-text-secondary); font-size: 13px;">
+                            "Пользователь видит документы только отмеченных организаций. Суперадмину доступны все организации."
+                        </p>
+                        {move || {
+                            organizations
+                                .get()
+                                .into_iter()
+                                .map(|organization| {
+                                    let organization_id = organization.id.clone();
+                                    let organization_id_toggle = organization.id.clone();
+                                    view! {
+                                        <label style="display: flex; gap: var(--spacing-sm); align-items: center; padding: 4px 0;">
+                                            <input
+                                                type="checkbox"
+                                                prop:checked=move || member_organization_ids.with(|ids| ids.contains(&organization_id))
+                                                on:change=move |ev| {
+                                                    let checked = event_target_checked(&ev);
+                                                    let id = organization_id_toggle.clone();
+                                                    member_organization_ids.update(|ids| {
+                                                        ids.retain(|existing| existing != &id);
+                                                        if checked {
+                                                            ids.push(id);
+                                                        }
+                                                    });
+                                                }
+                                                disabled=move || saving.get() || loading.get()
+                                            />
+                                            <span>{organization.name}</span>
+                                        </label>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </CardAnimated>
                 </div>
 
                 // ── Правая колонка ─────────────────────────────────────────
@@ -267,6 +425,8 @@ pub fn CreateUserPage(on_close: Callback<()>) -> impl IntoView {
     let full_name = RwSignal::new(String::new());
     let is_admin = RwSignal::new(false);
     let primary_role_code = RwSignal::new("viewer".to_string());
+    let active_from = RwSignal::new(String::new());
+    let active_until = RwSignal::new(String::new());
     let (saving, set_saving) = signal(false);
     let (error, set_error) = signal::<Option<String>>(None);
 
@@ -309,6 +469,8 @@ pub fn CreateUserPage(on_close: Callback<()>) -> impl IntoView {
             },
             is_admin: is_admin.get(),
             primary_role_code: primary_role_code.get(),
+            active_from: Some(active_from.get()).filter(|v| !v.is_empty()),
+            active_until: Some(active_until.get()).filter(|v| !v.is_empty()),
         };
 
         set_saving.set(true);
@@ -395,12 +557,32 @@ pub fn CreateUserPage(on_close: Callback<()>) -> impl IntoView {
                                     disabled=move || saving.get()
                                 >
                                     <option value="viewer">"viewer — только просмотр"</option>
+                                    <option value="warehouse">"warehouse — склад, без сумм"</option>
                                     <option value="operator">"operator — операционная работа"</option>
                                     <option value="manager">"manager — полный доступ"</option>
                                     <option value="admin">"admin — администратор системы"</option>
                                 </select>
                             </div>
 
+                            <div class="form__group">
+                                <label class="form__label">"Срок доступа"</label>
+                                <div style="display: flex; gap: var(--spacing-sm); align-items: center;">
+                                    "с"
+                                    <DateInput
+                                        value=active_from
+                                        on_change=move |v: String| active_from.set(v)
+                                    />
+                                    "по"
+                                    <DateInput
+                                        value=active_until
+                                        on_change=move |v: String| active_until.set(v)
+                                    />
+                                </div>
+                                <p style="margin: var(--spacing-xs) 0 0; color: var(--color-text-secondary); font-size: 13px;">
+                                    "Для внешних бухгалтеров и подрядчиков. Пусто — без ограничения."
+                                </p>
+                            </div>
+
                             <div style="display: flex; gap: var(--spacing-lg); margin-top: var(--spacing-sm);">
                                 <Checkbox checked=is_admin label="Суперадмин (is_admin bypass)" />
                             </div>
@@ -485,6 +667,8 @@ where
             },
             is_admin: is_admin.get(),
             primary_role_code: primary_role_code.get(),
+            active_from: None,
+            active_until: None,
         };
 
         set_is_saving.set(true);
@@ -570,6 +754,7 @@ where
                             on:change=move |ev| primary_role_code.set(event_target_value(&ev))
                         >
                             <option value="viewer">"viewer — только просмотр"</option>
+                            <option value="warehouse">"warehouse — склад, без сумм"</option>
                             <option value="operator">"operator — операционная работа"</option>
                             <option value="manager">"manager — полный доступ"</option>
                             <option value="admin">"admin — администратор системы"</option>
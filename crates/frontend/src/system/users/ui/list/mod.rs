@@ -1,6 +1,7 @@
 mod state;
 
-use contracts::system::users::User;
+use chrono::Utc;
+use contracts::system::users::{days_until_expiry, ExpiringUserDto, User, EXPIRY_REMINDER_DAYS};
 use gloo_timers::future::TimeoutFuture;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
@@ -11,7 +12,7 @@ use crate::shared::components::pagination_controls::PaginationControls;
 use crate::shared::components::table::{
     TableCellCheckbox, TableCrosshairHighlight, TableHeaderCheckbox,
 };
-use crate::shared::date_utils::format_datetime;
+use crate::shared::date_utils::{format_date, format_datetime};
 use crate::shared::icons::icon;
 use crate::shared::list_utils::{get_sort_class, get_sort_indicator, sort_list, Sortable};
 use crate::shared::page_frame::PageFrame;
@@ -24,6 +25,8 @@ use std::collections::HashSet;
 
 const TABLE_ID: &str = "sys-users-table";
 const COLUMN_WIDTHS_KEY: &str = "sys_users_column_widths";
+/// Горизонт отчёта «Истекающие учётные записи», дней.
+const EXPIRING_REPORT_DAYS: i64 = 30;
 
 fn primary_role_label(code: &str) -> &'static str {
     match code {
@@ -31,6 +34,7 @@ fn primary_role_label(code: &str) -> &'static str {
         "manager" => "manager",
         "operator" => "operator",
         "viewer" => "viewer",
+        "warehouse" => "warehouse",
         _ => "viewer",
     }
 }
@@ -44,6 +48,17 @@ fn primary_role_badge_class(code: &str) -> &'static str {
     }
 }
 
+/// Бейдж срока доступа: истёк / скоро истекает / без отметки.
+fn access_badge(days_left: i64) -> Option<(&'static str, String)> {
+    if days_left < 0 {
+        Some(("badge badge--error", "истёк".to_string()))
+    } else if days_left <= EXPIRY_REMINDER_DAYS {
+        Some(("badge badge--warning", format!("{} дн.", days_left)))
+    } else {
+        None
+    }
+}
+
 impl Sortable for User {
     fn compare_by_field(&self, other: &Self, field: &str) -> std::cmp::Ordering {
         match field {
@@ -66,6 +81,13 @@ impl Sortable for User {
             "is_admin" => self.is_admin.cmp(&other.is_admin),
             "is_active" => self.is_active.cmp(&other.is_active),
             "primary_role_code" => self.primary_role_code.cmp(&other.primary_role_code),
+            // Без ограничения срока — в конце при сортировке по возрастанию
+            "active_until" => match (&self.active_until, &other.active_until) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            },
             "created_at" => self.created_at.cmp(&other.created_at),
             "last_login_at" => self
                 .last_login_at
@@ -105,6 +127,7 @@ fn UsersList() -> impl IntoView {
     let (error, set_error) = signal::<Option<String>>(None);
     let (loading, set_loading) = signal(false);
     let selected: RwSignal<HashSet<String>> = RwSignal::new(HashSet::new());
+    let expiring: RwSignal<Vec<ExpiringUserDto>> = RwSignal::new(Vec::new());
 
     let global_ctx = use_context::<AppGlobalContext>();
 
@@ -168,6 +191,13 @@ fn UsersList() -> impl IntoView {
                     set_loading.set(false);
                 }
             }
+            match api::fetch_expiring(EXPIRING_REPORT_DAYS).await {
+                Ok(data) => expiring.set(data),
+                Err(e) => set_error.set(Some(format!(
+                    "Не удалось загрузить истекающие учётные записи: {}",
+                    e
+                ))),
+            }
         });
     };
 
@@ -339,6 +369,89 @@ fn UsersList() -> impl IntoView {
                     </div>
                 </div>
 
+                {move || {
+                    let rows = expiring.get();
+                    (!rows.is_empty()).then(|| view! {
+                        <div class="filter-panel">
+                            <div class="filter-panel-header">
+                                <div class="filter-panel-header__left">
+                                    {icon("alert-triangle")}
+                                    <span class="filter-panel__title">
+                                        {format!("Истекающие учётные записи (до {} дн.)", EXPIRING_REPORT_DAYS)}
+                                    </span>
+                                    <Badge>{rows.len().to_string()}</Badge>
+                                </div>
+                            </div>
+                            <div class="filter-panel-content">
+                                <Table attr:style="width: 100%;">
+                                    <TableHeader>
+                                        <TableRow>
+                                            <TableHeaderCell>"Логин"</TableHeaderCell>
+                                            <TableHeaderCell>"ФИО"</TableHeaderCell>
+                                            <TableHeaderCell>"Email"</TableHeaderCell>
+                                            <TableHeaderCell>"Доступ до"</TableHeaderCell>
+                                            <TableHeaderCell>"Осталось"</TableHeaderCell>
+                                            <TableHeaderCell>"Статус"</TableHeaderCell>
+                                            <TableHeaderCell>"Последний вход"</TableHeaderCell>
+                                        </TableRow>
+                                    </TableHeader>
+                                    <TableBody>
+                                        {rows
+                                            .into_iter()
+                                            .map(|row| {
+                                                let user_id = row.id.clone();
+                                                let username = row.username.clone();
+                                                let (badge_class, badge_text) = access_badge(row.days_left)
+                                                    .unwrap_or(("badge badge--neutral", format!("{} дн.", row.days_left)));
+                                                view! {
+                                                    <TableRow>
+                                                        <TableCell>
+                                                            <TableCellLayout truncate=true>
+                                                                <span
+                                                                    class="table__link"
+                                                                    on:click=move |_| open_user_details(user_id.clone(), username.clone())
+                                                                >
+                                                                    {row.username.clone()}
+                                                                </span>
+                                                            </TableCellLayout>
+                                                        </TableCell>
+                                                        <TableCell>
+                                                            <TableCellLayout truncate=true>
+                                                                {row.full_name.clone().unwrap_or_default()}
+                                                            </TableCellLayout>
+                                                        </TableCell>
+                                                        <TableCell>
+                                                            <TableCellLayout truncate=true>
+                                                                {row.email.clone().unwrap_or_default()}
+                                                            </TableCellLayout>
+                                                        </TableCell>
+                                                        <TableCell>
+                                                            <TableCellLayout>{format_date(&row.active_until)}</TableCellLayout>
+                                                        </TableCell>
+                                                        <TableCell>
+                                                            <TableCellLayout>
+                                                                <span class=badge_class>{badge_text}</span>
+                                                            </TableCellLayout>
+                                                        </TableCell>
+                                                        <TableCell>
+                                                            <TableCellLayout>
+                                                                {if row.is_active { "Активен" } else { "Заблок." }}
+                                                            </TableCellLayout>
+                                                        </TableCell>
+                                                        <TableCell>
+                                                            <TableCellLayout>{format_ts_opt(&row.last_login_at)}</TableCellLayout>
+                                                        </TableCell>
+                                                    </TableRow>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </TableBody>
+                                </Table>
+                            </div>
+                        </div>
+                    })
+                }}
+
                 <div class="table-wrapper">
                     <TableCrosshairHighlight table_id=TABLE_ID.to_string() />
 
@@ -399,6 +512,14 @@ fn UsersList() -> impl IntoView {
                                         </span>
                                     </div>
                                 </TableHeaderCell>
+                                <TableHeaderCell resizable=false class="resizable" min_width=110.0>
+                                    <div class="table__sortable-header" style="cursor:pointer;" on:click=toggle_sort("active_until")>
+                                        "Доступ до"
+                                        <span class=move || state.with(|s| get_sort_class(&s.sort_field, "active_until"))>
+                                            {move || get_sort_indicator(&state.with(|s| s.sort_field.clone()), "active_until", state.with(|s| s.sort_ascending))}
+                                        </span>
+                                    </div>
+                                </TableHeaderCell>
                                 <TableHeaderCell resizable=false class="resizable" min_width=130.0>
                                     <div class="table__sortable-header" style="cursor:pointer;" on:click=toggle_sort("created_at")>
                                         "Создан"
@@ -428,6 +549,16 @@ fn UsersList() -> impl IntoView {
                                     let username_for_click = user.username.clone();
                                     let created = format_ts(&user.created_at);
                                     let last_login = format_ts_opt(&user.last_login_at);
+                                    let access_until = user
+                                        .active_until
+                                        .as_deref()
+                                        .map(format_date)
+                                        .unwrap_or_else(|| "-".to_string());
+                                    let access_state = days_until_expiry(
+                                        user.active_until.as_deref(),
+                                        Utc::now().date_naive(),
+                                    )
+                                    .and_then(access_badge);
                                     view! {
                                         <TableRow>
                                             <TableCellCheckbox
@@ -480,6 +611,14 @@ fn UsersList() -> impl IntoView {
                                                     }}
                                                 </TableCellLayout>
                                             </TableCell>
+                                            <TableCell>
+                                                <TableCellLayout>
+                                                    {access_until}
+                                                    {access_state.map(|(class, text)| view! {
+                                                        " " <span class=class>{text}</span>
+                                                    })}
+                                                </TableCellLayout>
+                                            </TableCell>
                                             <TableCell>
                                                 <TableCellLayout>{created}</TableCellLayout>
                                             </TableCell>
@@ -6,3 +6,4 @@ pub mod u505_match_nomenclature;
 pub mod u506_import_from_lemanapro;
 pub mod u507_import_from_erp;
 pub mod u508_repost_documents;
+pub mod u509_link_ozon_skus;
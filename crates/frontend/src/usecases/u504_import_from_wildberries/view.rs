@@ -418,6 +418,16 @@ fn ServiceRow(
                                 } else {
                                     view! { <></> }.into_any()
                                 }}
+                                {move || progress.get()
+                                    .filter(|prog| prog.raw_payloads_stored + prog.raw_payloads_deduplicated > 0)
+                                    .map(|prog| view! {
+                                        <div style="padding: 2px 0; font-size: var(--font-size-sm); color: var(--color-text-secondary);">
+                                            {format!(
+                                                "Raw JSON: сохранено {}, повторных выгрузок без изменений {}",
+                                                prog.raw_payloads_stored, prog.raw_payloads_deduplicated
+                                            )}
+                                        </div>
+                                    })}
                             </div>
                         }.into_any()
                     } else {
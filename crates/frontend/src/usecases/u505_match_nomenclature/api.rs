@@ -1,8 +1,13 @@
+use contracts::usecases::u505_match_nomenclature::suggestions::{
+    MatchAutoAcceptRequest, MatchDecisionRequest, MatchDecisionResponse,
+    MatchSuggestionListResponse, MatchSuggestionQuery,
+};
 use contracts::usecases::u505_match_nomenclature::{MatchProgress, MatchRequest, MatchResponse};
 use serde_json;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{window, RequestInit, RequestMode, Response};
 
+use crate::shared::api_client;
 use crate::shared::api_utils::api_base;
 
 /// API клиент для UseCase u505
@@ -87,3 +92,45 @@ pub async fn get_progress(session_id: &str) -> Result<MatchProgress, String> {
 
     Ok(progress)
 }
+
+/// Товары без связи и кандидаты номенклатуры
+pub async fn get_suggestions(
+    query: &MatchSuggestionQuery,
+) -> Result<MatchSuggestionListResponse, String> {
+    let mut params = Vec::new();
+    if let Some(marketplace_id) = &query.marketplace_id {
+        params.push(format!("marketplaceId={}", marketplace_id));
+    }
+    if let Some(min_score) = query.min_score {
+        params.push(format!("minScore={}", min_score));
+    }
+    if let Some(limit) = query.limit {
+        params.push(format!("limit={}", limit));
+    }
+    if let Some(offset) = query.offset {
+        params.push(format!("offset={}", offset));
+    }
+    let mut path = "/api/u505/suggestions".to_string();
+    if !params.is_empty() {
+        path = format!("{}?{}", path, params.join("&"));
+    }
+    api_client::get_json(&path).await.map_err(|e| e.to_string())
+}
+
+/// Принять / отклонить предложения
+pub async fn apply_decisions(
+    request: &MatchDecisionRequest,
+) -> Result<MatchDecisionResponse, String> {
+    api_client::post_json("/api/u505/suggestions/decisions", request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Принять все уверенные предложения
+pub async fn accept_high_confidence(
+    request: &MatchAutoAcceptRequest,
+) -> Result<MatchDecisionResponse, String> {
+    api_client::post_json("/api/u505/suggestions/accept-high-confidence", request)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,4 +1,5 @@
 pub mod api;
+pub mod suggestions_panel;
 pub mod view;
 
 pub use view::MatchNomenclatureView;
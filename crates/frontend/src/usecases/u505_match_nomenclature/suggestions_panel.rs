@@ -0,0 +1,242 @@
+use contracts::usecases::u505_match_nomenclature::suggestions::{
+    MatchAutoAcceptRequest, MatchDecisionDto, MatchDecisionRequest, MatchDecisionResponse,
+    MatchRule, MatchSuggestionDto, MatchSuggestionQuery, HIGH_CONFIDENCE_SCORE,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+const PAGE_SIZE: usize = 200;
+
+fn rule_label(rule: MatchRule) -> &'static str {
+    match rule {
+        MatchRule::Barcode => "Штрихкод",
+        MatchRule::Exact => "Артикул",
+        MatchRule::Normalized => "Артикул без разделителей",
+        MatchRule::Prefix => "Начало артикула",
+        MatchRule::Name => "Наименование",
+    }
+}
+
+fn result_message(response: &MatchDecisionResponse) -> String {
+    let mut message = format!(
+        "Принято: {}, отклонено: {}",
+        response.accepted, response.rejected
+    );
+    if !response.errors.is_empty() {
+        message.push_str(&format!(
+            ". Ошибки ({}): {}",
+            response.errors.len(),
+            response.errors.join("; ")
+        ));
+    }
+    message
+}
+
+/// Блок страницы сопоставления: предложенные связи товаров маркетплейсов с
+/// номенклатурой (по штрихкоду, артикулу и наименованию) с принятием и отклонением.
+#[component]
+pub fn MatchSuggestionsPanel() -> impl IntoView {
+    let (items, set_items) = signal(Vec::<MatchSuggestionDto>::new());
+    let (total, set_total) = signal(0usize);
+    let (message, set_message) = signal(Option::<String>::None);
+    let (error_message, set_error_message) = signal(Option::<String>::None);
+    let (is_loading, set_is_loading) = signal(false);
+
+    let load = move || {
+        set_is_loading.set(true);
+        spawn_local(async move {
+            let query = MatchSuggestionQuery {
+                limit: Some(PAGE_SIZE),
+                ..Default::default()
+            };
+            match super::api::get_suggestions(&query).await {
+                Ok(data) => {
+                    set_total.set(data.total);
+                    set_items.set(data.items);
+                }
+                Err(e) => set_error_message.set(Some(format!("Ошибка загрузки: {}", e))),
+            }
+            set_is_loading.set(false);
+        });
+    };
+    load();
+
+    let decide = move |product_ref: String, nomenclature_ref: String, accept: bool| {
+        set_error_message.set(None);
+        spawn_local(async move {
+            let request = MatchDecisionRequest {
+                decisions: vec![MatchDecisionDto {
+                    marketplace_product_ref: product_ref.clone(),
+                    nomenclature_ref: nomenclature_ref.clone(),
+                    accept,
+                }],
+            };
+            match super::api::apply_decisions(&request).await {
+                Ok(response) => {
+                    if !response.errors.is_empty() {
+                        set_error_message.set(Some(response.errors.join("; ")));
+                        return;
+                    }
+                    // Принятый товар уходит из списка целиком, отклонённый кандидат — один.
+                    let mut removed = false;
+                    set_items.update(|items| {
+                        if let Some(pos) = items
+                            .iter()
+                            .position(|s| s.marketplace_product_ref == product_ref)
+                        {
+                            let suggestion = &mut items[pos];
+                            suggestion
+                                .candidates
+                                .retain(|c| !accept && c.nomenclature_ref != nomenclature_ref);
+                            if suggestion.candidates.is_empty() {
+                                items.remove(pos);
+                                removed = true;
+                            }
+                        }
+                    });
+                    if removed {
+                        set_total.update(|total| *total = total.saturating_sub(1));
+                    }
+                }
+                Err(e) => set_error_message.set(Some(format!("Ошибка: {}", e))),
+            }
+        });
+    };
+
+    let accept_confident = move |_| {
+        set_error_message.set(None);
+        set_message.set(None);
+        set_is_loading.set(true);
+        spawn_local(async move {
+            match super::api::accept_high_confidence(&MatchAutoAcceptRequest::default()).await {
+                Ok(response) => {
+                    set_message.set(Some(result_message(&response)));
+                    load();
+                }
+                Err(e) => {
+                    set_error_message.set(Some(format!("Ошибка: {}", e)));
+                    set_is_loading.set(false);
+                }
+            }
+        });
+    };
+
+    view! {
+        <section class="u505-match__section">
+            <h2 class="u505-match__section-title">"Предложения сопоставления"</h2>
+            <div class="u505-match__lead">
+                "Товары без связи с номенклатурой и кандидаты по штрихкоду, артикулу и наименованию. "
+                "Оценка учитывает прежние решения по бренду; отклонённые пары больше не предлагаются."
+            </div>
+
+            <div class="u505-match__actions">
+                <button
+                    class="u505-match__button"
+                    on:click=move |_| load()
+                    prop:disabled=move || is_loading.get()
+                >
+                    "Обновить"
+                </button>
+                <button
+                    class="u505-match__button"
+                    on:click=accept_confident
+                    prop:disabled=move || is_loading.get() || items.get().is_empty()
+                >
+                    {format!("Принять уверенные (оценка ≥ {:.2})", HIGH_CONFIDENCE_SCORE)}
+                </button>
+            </div>
+
+            {move || message.get().map(|msg| view! { <div class="u505-match__message">{msg}</div> })}
+            {move || {
+                error_message.get().map(|msg| {
+                    view! { <div class="u505-match__message u505-match__message--error">{msg}</div> }
+                })
+            }}
+
+            {move || {
+                let data = items.get();
+                if data.is_empty() {
+                    return view! {
+                        <div class="u505-match__message">
+                            {if is_loading.get() { "Загрузка..." } else { "Предложений нет." }}
+                        </div>
+                    }
+                    .into_any();
+                }
+                view! {
+                    <div class="u505-match__lead">
+                        {format!("Товаров с предложениями: {}", total.get())}
+                        {if total.get() > data.len() { " (показаны самые уверенные)" } else { "" }}
+                    </div>
+                    <div class="table-wrapper">
+                        <table class="table__data table--striped">
+                            <thead class="table__head">
+                                <tr class="table__header-row">
+                                    <th class="table__header-cell">"Артикул МП"</th>
+                                    <th class="table__header-cell">"Товар"</th>
+                                    <th class="table__header-cell">"Бренд"</th>
+                                    <th class="table__header-cell">"Артикул 1С"</th>
+                                    <th class="table__header-cell">"Номенклатура"</th>
+                                    <th class="table__header-cell">"Правило"</th>
+                                    <th class="table__header-cell">"Оценка"</th>
+                                    <th class="table__header-cell"></th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {data.into_iter().flat_map(|suggestion| {
+                                    let product_ref = suggestion.marketplace_product_ref.clone();
+                                    suggestion.candidates.into_iter().enumerate().map(move |(i, candidate)| {
+                                        let first = i == 0;
+                                        let accept_refs = (product_ref.clone(), candidate.nomenclature_ref.clone());
+                                        let reject_refs = accept_refs.clone();
+                                        view! {
+                                            <tr class="table__row">
+                                                <td class="table__cell">
+                                                    {if first { suggestion.article.clone() } else { String::new() }}
+                                                </td>
+                                                <td class="table__cell">
+                                                    {if first { suggestion.description.clone() } else { String::new() }}
+                                                </td>
+                                                <td class="table__cell">
+                                                    {if first { suggestion.brand.clone().unwrap_or_default() } else { String::new() }}
+                                                </td>
+                                                <td class="table__cell">{candidate.article}</td>
+                                                <td class="table__cell">{candidate.description}</td>
+                                                <td class="table__cell">{rule_label(candidate.rule)}</td>
+                                                <td class="table__cell table__cell--right">
+                                                    {format!("{:.2}", candidate.score)}
+                                                </td>
+                                                <td class="table__cell">
+                                                    <button
+                                                        class="u505-match__button"
+                                                        on:click=move |_| {
+                                                            let (product, nomenclature) = accept_refs.clone();
+                                                            decide(product, nomenclature, true)
+                                                        }
+                                                    >
+                                                        "Принять"
+                                                    </button>
+                                                    " "
+                                                    <button
+                                                        class="u505-match__button"
+                                                        on:click=move |_| {
+                                                            let (product, nomenclature) = reject_refs.clone();
+                                                            decide(product, nomenclature, false)
+                                                        }
+                                                    >
+                                                        "Отклонить"
+                                                    </button>
+                                                </td>
+                                            </tr>
+                                        }
+                                    }).collect::<Vec<_>>()
+                                }).collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                }
+                .into_any()
+            }}
+        </section>
+    }
+}
@@ -1,5 +1,8 @@
 use crate::shared::page_frame::PageFrame;
 use crate::shared::page_standard::PAGE_CAT_USECASE;
+use crate::usecases::u509_link_ozon_skus::UnmatchedOzonSkusPanel;
+
+use super::suggestions_panel::MatchSuggestionsPanel;
 use contracts::usecases::u505_match_nomenclature::{
     progress::MatchStatus, MatchProgress, MatchRequest,
 };
@@ -237,6 +240,10 @@ pub fn MatchNomenclatureView() -> impl IntoView {
                             }
                         })
                     }}
+
+                    <MatchSuggestionsPanel />
+
+                    <UnmatchedOzonSkusPanel />
                 </div>
             </div>
         </PageFrame>
@@ -8,8 +8,10 @@ use contracts::projections::p916_mp_sales_funnel_turnovers::dto::{
 use contracts::usecases::u508_repost_documents::{
     aggregate::AggregateOption,
     aggregate_request::AggregateRepostRequest,
+    period_request::PeriodRepostRequest,
     progress::{RepostProgress, RepostStatus},
     projection::ProjectionOption,
+    rebuild_request::REBUILDABLE_PROJECTIONS,
     request::RepostRequest,
 };
 use leptos::prelude::*;
@@ -82,11 +84,22 @@ pub fn RepostDocumentsWidget() -> impl IntoView {
 
     let (aggregates, set_aggregates) = signal(Vec::<AggregateOption>::new());
     let (selected_aggregate, set_selected_aggregate) = signal(String::new());
-    let (aggregate_date_from, set_aggregate_date_from) = signal(default_date_from);
-    let (aggregate_date_to, set_aggregate_date_to) = signal(default_date_to);
+    let (aggregate_date_from, set_aggregate_date_from) = signal(default_date_from.clone());
+    let (aggregate_date_to, set_aggregate_date_to) = signal(default_date_to.clone());
     let aggregate_only_posted = RwSignal::new(false);
     let aggregate_connection_mp_refs = RwSignal::new(Vec::<String>::new());
 
+    let rebuild_projection = RwSignal::new(REBUILDABLE_PROJECTIONS[0].0.to_string());
+    let rebuild_date_from = RwSignal::new(default_date_from.clone());
+    let rebuild_date_to = RwSignal::new(default_date_to.clone());
+    let (is_starting_rebuild, set_is_starting_rebuild) = signal(false);
+
+    let period_date_from = RwSignal::new(default_date_from);
+    let period_date_to = RwSignal::new(default_date_to);
+    let period_only_posted = RwSignal::new(true);
+    let period_connection_mp_refs = RwSignal::new(Vec::<String>::new());
+    let (is_starting_period, set_is_starting_period) = signal(false);
+
     let funnel_date_from = RwSignal::new(default_date_from_funnel);
     let funnel_date_to = RwSignal::new(default_date_to_funnel);
     let funnel_connection_mp_refs = RwSignal::new(Vec::<String>::new());
@@ -222,6 +235,30 @@ pub fn RepostDocumentsWidget() -> impl IntoView {
         });
     };
 
+    let on_start_rebuild = move |_| {
+        let projection = rebuild_projection.get();
+        let date_from = rebuild_date_from.get();
+        let date_to = rebuild_date_to.get();
+
+        clear_storage();
+        set_is_starting_rebuild.set(true);
+        set_error_msg.set(String::new());
+        set_progress.set(None);
+
+        spawn_local(async move {
+            match api::start_projection_rebuild(&projection, &date_from, &date_to).await {
+                Ok(response) => {
+                    save_session_id(&response.session_id);
+                    set_session_id.set(Some(response.session_id));
+                }
+                Err(error) => {
+                    set_error_msg.set(format!("Ошибка запуска: {}", error));
+                }
+            }
+            set_is_starting_rebuild.set(false);
+        });
+    };
+
     let on_start_aggregate = move |_| {
         let aggregate_key = selected_aggregate.get();
         if aggregate_key.is_empty() {
@@ -256,6 +293,34 @@ pub fn RepostDocumentsWidget() -> impl IntoView {
         });
     };
 
+    let on_start_period = move |_| {
+        let request = PeriodRepostRequest {
+            date_from: period_date_from.get(),
+            date_to: period_date_to.get(),
+            aggregate_keys: Vec::new(),
+            only_posted: period_only_posted.get(),
+            connection_mp_refs: period_connection_mp_refs.get(),
+        };
+
+        clear_storage();
+        set_is_starting_period.set(true);
+        set_error_msg.set(String::new());
+        set_progress.set(None);
+
+        spawn_local(async move {
+            match api::start_period_repost(request).await {
+                Ok(response) => {
+                    save_session_id(&response.session_id);
+                    set_session_id.set(Some(response.session_id));
+                }
+                Err(error) => {
+                    set_error_msg.set(format!("Ошибка запуска: {}", error));
+                }
+            }
+            set_is_starting_period.set(false);
+        });
+    };
+
     let on_start_funnel = move |_| {
         let request = FunnelRebuildRequest {
             date_from: funnel_date_from.get(),
@@ -400,6 +465,75 @@ pub fn RepostDocumentsWidget() -> impl IntoView {
                     </Card>
                 </div>
 
+                <div style="margin-left:16px;margin-right:16px;">
+                    <Card>
+                        <Flex vertical=true gap=FlexGap::Small>
+                            <div style="font-weight:600;">"Пересборка проекции с нуля"</div>
+
+                            <div class="doc-filters__row">
+                                <Button
+                                    appearance=ButtonAppearance::Primary
+                                    on_click=on_start_rebuild
+                                    disabled=move || is_starting_rebuild.get() || session_id.get().is_some()
+                                >
+                                    {move || {
+                                        if is_starting_rebuild.get() {
+                                            "Запуск..."
+                                        } else if session_id.get().is_some() {
+                                            "В работе"
+                                        } else {
+                                            "Очистить и пересобрать"
+                                        }
+                                    }}
+                                </Button>
+
+                                <Flex vertical=true gap=FlexGap::Small style="flex:1;min-width:0;">
+                                    <div style="font-size:var(--font-size-sm);color:var(--color-text-secondary);">
+                                        "Строки проекции за период удаляются (включая архив), затем все проведённые документы-источники периода проводятся заново. Для правок логики проведения: документы, которых раньше не было в проекции, тоже попадут в неё. p904 пересобирается массово: документы не пересохраняются, старые строки видны до замены одной транзакцией."
+                                    </div>
+                                    <div class="doc-filter">
+                                        <label class="doc-filter__label">"Проекция:"</label>
+                                        <select
+                                            class="doc-filter__select"
+                                            style="min-width:280px;"
+                                            on:change=move |ev| rebuild_projection.set(event_target_value(&ev))
+                                        >
+                                            {REBUILDABLE_PROJECTIONS
+                                                .iter()
+                                                .map(|(key, label)| {
+                                                    let key = key.to_string();
+                                                    let is_selected = key == rebuild_projection.get_untracked();
+                                                    view! {
+                                                        <option selected=is_selected value=key>
+                                                            {*label}
+                                                        </option>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </select>
+                                    </div>
+                                    <div class="doc-filter">
+                                        <label class="doc-filter__label">"Период:"</label>
+                                        <input
+                                            type="date"
+                                            class="doc-filter__input"
+                                            prop:value=move || rebuild_date_from.get()
+                                            on:change=move |ev| rebuild_date_from.set(event_target_value(&ev))
+                                        />
+                                        <span>"—"</span>
+                                        <input
+                                            type="date"
+                                            class="doc-filter__input"
+                                            prop:value=move || rebuild_date_to.get()
+                                            on:change=move |ev| rebuild_date_to.set(event_target_value(&ev))
+                                        />
+                                    </div>
+                                </Flex>
+                            </div>
+                        </Flex>
+                    </Card>
+                </div>
+
                 <div style="margin-left:16px;margin-right:16px;">
                     <Card>
                         <Flex vertical=true gap=FlexGap::Small>
@@ -514,6 +648,67 @@ pub fn RepostDocumentsWidget() -> impl IntoView {
                     </Card>
                 </div>
 
+                <div style="margin-left:16px;margin-right:16px;">
+                    <Card>
+                        <Flex vertical=true gap=FlexGap::Small>
+                            <div style="font-weight:600;">"Полное перепроведение периода"</div>
+
+                            <div class="doc-filters__row">
+                                <Button
+                                    appearance=ButtonAppearance::Primary
+                                    on_click=on_start_period
+                                    disabled=move || is_starting_period.get() || session_id.get().is_some()
+                                >
+                                    {move || {
+                                        if is_starting_period.get() {
+                                            "Запуск..."
+                                        } else if session_id.get().is_some() {
+                                            "В работе"
+                                        } else {
+                                            "Распровести и провести"
+                                        }
+                                    }}
+                                </Button>
+
+                                <Flex vertical=true gap=FlexGap::Small style="flex:1;min-width:0;">
+                                    <div style="font-size:var(--font-size-sm);color:var(--color-text-secondary);">
+                                        "Все документы периода распроводятся и проводятся заново по шагам: поступления, выпуск, заказы WB, продажи WB, реклама WB, реализация YM. Ошибка одного документа не останавливает прогон."
+                                    </div>
+                                    <div class="doc-filter">
+                                        <label class="doc-filter__label">"Период:"</label>
+                                        <input
+                                            type="date"
+                                            class="doc-filter__input"
+                                            prop:value=move || period_date_from.get()
+                                            on:change=move |ev| period_date_from.set(event_target_value(&ev))
+                                        />
+                                        <span>"—"</span>
+                                        <input
+                                            type="date"
+                                            class="doc-filter__input"
+                                            prop:value=move || period_date_to.get()
+                                            on:change=move |ev| period_date_to.set(event_target_value(&ev))
+                                        />
+                                    </div>
+                                    <div class="doc-filter" style="align-items:flex-start;">
+                                        <label class="doc-filter__label">"Кабинеты WB:"</label>
+                                        <div style="display:flex;flex-direction:column;gap:6px;">
+                                            <ConnectionMpMultiSelect selected=period_connection_mp_refs />
+                                            <span style="font-size:var(--font-size-xs);color:var(--color-text-secondary);">
+                                                "Фильтр применяется к продажам WB; если ничего не выбрано — все кабинеты"
+                                            </span>
+                                        </div>
+                                    </div>
+                                    <Checkbox
+                                        checked=period_only_posted
+                                        label="Только проведенные"
+                                    />
+                                </Flex>
+                            </div>
+                        </Flex>
+                    </Card>
+                </div>
+
                 <div style="margin-left:16px;margin-right:16px;">
                     <Card>
                         <Flex vertical=true gap=FlexGap::Small>
@@ -611,6 +806,17 @@ pub fn RepostDocumentsWidget() -> impl IntoView {
                                                 </span>
                                             </div>
 
+                                            {if current_progress.truncated_rows > 0 {
+                                                view! {
+                                                    <div style="font-size:var(--font-size-sm);color:var(--color-text-secondary);">
+                                                        {format!("Удалено строк проекции: {}", current_progress.truncated_rows)}
+                                                    </div>
+                                                }
+                                                .into_any()
+                                            } else {
+                                                view! { <></> }.into_any()
+                                            }}
+
                                             {if let Some(current_item) = current_progress.current_item {
                                                 view! {
                                                     <div style="font-size:var(--font-size-sm);color:var(--color-text-secondary);">
@@ -0,0 +1,97 @@
+use contracts::usecases::u509_link_ozon_skus::{
+    LinkProgress, LinkRequest, LinkResponse, UnmatchedSkuReport,
+};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{window, RequestInit, RequestMode, Response};
+
+use crate::shared::api_utils::api_base;
+
+/// GET-запрос с разбором JSON-ответа
+async fn get_json<T: serde::de::DeserializeOwned>(url: &str) -> Result<T, String> {
+    let window = window().ok_or("No window object")?;
+
+    let opts = RequestInit::new();
+    opts.set_method("GET");
+    opts.set_mode(RequestMode::Cors);
+
+    let request = web_sys::Request::new_with_str_and_init(url, &opts)
+        .map_err(|e| format!("Failed to create request: {:?}", e))?;
+
+    let response_value = wasm_bindgen_futures::JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(|e| format!("Fetch failed: {:?}", e))?;
+
+    let response: Response = response_value.dyn_into().map_err(|_| "Not a Response")?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    let json = wasm_bindgen_futures::JsFuture::from(
+        response
+            .json()
+            .map_err(|e| format!("Failed to parse JSON: {:?}", e))?,
+    )
+    .await
+    .map_err(|e| format!("Failed to get JSON: {:?}", e))?;
+
+    serde_wasm_bindgen::from_value(json).map_err(|e| e.to_string())
+}
+
+/// Запустить привязку строк транзакций Ozon к товарам по SKU
+pub async fn start_linking(request: LinkRequest) -> Result<LinkResponse, String> {
+    let window = window().ok_or("No window object")?;
+
+    let body = serde_json::to_string(&request).map_err(|e| e.to_string())?;
+
+    let opts = RequestInit::new();
+    opts.set_method("POST");
+    opts.set_mode(RequestMode::Cors);
+    opts.set_body(&JsValue::from_str(&body));
+
+    let request = web_sys::Request::new_with_str_and_init(
+        &format!("{}/api/u509/link/start", api_base()),
+        &opts,
+    )
+    .map_err(|e| format!("Failed to create request: {:?}", e))?;
+
+    request
+        .headers()
+        .set("Content-Type", "application/json")
+        .map_err(|e| format!("Failed to set header: {:?}", e))?;
+
+    let response_value = wasm_bindgen_futures::JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(|e| format!("Fetch failed: {:?}", e))?;
+
+    let response: Response = response_value.dyn_into().map_err(|_| "Not a Response")?;
+
+    if !response.ok() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+
+    let json = wasm_bindgen_futures::JsFuture::from(
+        response
+            .json()
+            .map_err(|e| format!("Failed to parse JSON: {:?}", e))?,
+    )
+    .await
+    .map_err(|e| format!("Failed to get JSON: {:?}", e))?;
+
+    serde_wasm_bindgen::from_value(json).map_err(|e| e.to_string())
+}
+
+/// Получить прогресс привязки
+pub async fn get_progress(session_id: &str) -> Result<LinkProgress, String> {
+    get_json(&format!(
+        "{}/api/u509/link/{}/progress",
+        api_base(),
+        session_id
+    ))
+    .await
+}
+
+/// Отчёт по SKU без товара маркетплейса
+pub async fn get_unmatched() -> Result<UnmatchedSkuReport, String> {
+    get_json(&format!("{}/api/u509/unmatched", api_base())).await
+}
@@ -0,0 +1,4 @@
+pub mod api;
+pub mod view;
+
+pub use view::UnmatchedOzonSkusPanel;
@@ -0,0 +1,198 @@
+use contracts::usecases::u509_link_ozon_skus::{
+    LinkProgress, LinkRequest, LinkStatus, UnmatchedSkuReport,
+};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Блок страницы сопоставления: привязка транзакций Ozon к товарам по SKU и
+/// список SKU, для которых товар маркетплейса не найден.
+#[component]
+pub fn UnmatchedOzonSkusPanel() -> impl IntoView {
+    let (session_id, set_session_id) = signal(Option::<String>::None);
+    let (progress, set_progress) = signal(Option::<LinkProgress>::None);
+    let (report, set_report) = signal(Option::<UnmatchedSkuReport>::None);
+    let (error_message, set_error_message) = signal(Option::<String>::None);
+    let (is_loading, set_is_loading) = signal(false);
+    let (relink_existing, set_relink_existing) = signal(false);
+
+    let load_report = move || {
+        spawn_local(async move {
+            match super::api::get_unmatched().await {
+                Ok(data) => set_report.set(Some(data)),
+                Err(e) => set_error_message.set(Some(format!("Ошибка загрузки отчёта: {}", e))),
+            }
+        });
+    };
+    load_report();
+
+    let start_linking = move |_| {
+        set_error_message.set(None);
+        set_progress.set(None);
+        set_is_loading.set(true);
+
+        let request = LinkRequest {
+            relink_existing: relink_existing.get(),
+            ..Default::default()
+        };
+
+        spawn_local(async move {
+            match super::api::start_linking(request).await {
+                Ok(response) => set_session_id.set(Some(response.session_id)),
+                Err(e) => set_error_message.set(Some(format!("Ошибка запуска: {}", e))),
+            }
+            set_is_loading.set(false);
+        });
+    };
+
+    Effect::new(move || {
+        if let Some(sid) = session_id.get() {
+            spawn_local(async move {
+                loop {
+                    match super::api::get_progress(&sid).await {
+                        Ok(prog) => {
+                            let is_finished = !matches!(prog.status, LinkStatus::InProgress);
+                            set_progress.set(Some(prog));
+                            if is_finished {
+                                set_session_id.set(None);
+                                load_report();
+                                break;
+                            }
+                        }
+                        Err(_) => break,
+                    }
+                    gloo_timers::future::TimeoutFuture::new(2000).await;
+                }
+            });
+        }
+    });
+
+    let status_label = |status: &LinkStatus| match status {
+        LinkStatus::InProgress => "В процессе",
+        LinkStatus::Completed => "Завершено",
+        LinkStatus::CompletedWithErrors => "Завершено с ошибками",
+        LinkStatus::Failed => "Провалено",
+    };
+
+    view! {
+        <section class="u505-match__section">
+            <h2 class="u505-match__section-title">"Транзакции Ozon без товара"</h2>
+            <div class="u505-match__lead">
+                "Строки транзакций Ozon привязываются к товарам маркетплейса по SKU при импорте. "
+                "Перепривязка нужна после загрузки или исправления карточек товаров."
+            </div>
+
+            <div class="u505-match__options">
+                <label class="u505-match__checkbox">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || relink_existing.get()
+                        on:change=move |ev| set_relink_existing.set(event_target_checked(&ev))
+                    />
+                    <span>"Перепроверить уже привязанные строки"</span>
+                </label>
+            </div>
+
+            <div class="u505-match__actions">
+                <button
+                    class="u505-match__button"
+                    on:click=start_linking
+                    prop:disabled=move || is_loading.get() || session_id.get().is_some()
+                >
+                    {move || if session_id.get().is_some() {
+                        "Привязка выполняется..."
+                    } else {
+                        "Привязать по SKU"
+                    }}
+                </button>
+            </div>
+
+            {move || {
+                error_message.get().map(|msg| {
+                    view! { <div class="u505-match__message u505-match__message--error">{msg}</div> }
+                })
+            }}
+
+            {move || {
+                progress.get().map(|prog| {
+                    view! {
+                        <div class="u505-match__stats">
+                            <div class="u505-match__stat">
+                                <span class="u505-match__stat-label">"Статус"</span>
+                                <strong>{status_label(&prog.status)}</strong>
+                            </div>
+                            <div class="u505-match__stat">
+                                <span class="u505-match__stat-label">"Документов"</span>
+                                <strong>
+                                    {prog.processed}
+                                    {prog.total.map(|t| format!(" / {}", t)).unwrap_or_default()}
+                                </strong>
+                            </div>
+                            <div class="u505-match__stat">
+                                <span class="u505-match__stat-label">"Обновлено"</span>
+                                <strong>{prog.documents_updated}</strong>
+                            </div>
+                            <div class="u505-match__stat">
+                                <span class="u505-match__stat-label">"Привязано строк"</span>
+                                <strong>{prog.items_linked}</strong>
+                            </div>
+                            <div class="u505-match__stat">
+                                <span class="u505-match__stat-label">"Не найдено"</span>
+                                <strong>{prog.items_unmatched}</strong>
+                            </div>
+                            <div class="u505-match__stat">
+                                <span class="u505-match__stat-label">"Ошибки"</span>
+                                <strong>{prog.errors}</strong>
+                            </div>
+                        </div>
+                    }
+                })
+            }}
+
+            {move || {
+                report.get().map(|data| {
+                    if data.rows.is_empty() {
+                        return view! {
+                            <div class="u505-match__message">"Все строки транзакций привязаны к товарам."</div>
+                        }
+                        .into_any();
+                    }
+                    view! {
+                        <div class="u505-match__lead">
+                            {format!("Строк без товара: {}", data.total_items)}
+                            {if data.truncated { " (показаны самые частые SKU)" } else { "" }}
+                        </div>
+                        <div class="table-wrapper">
+                            <table class="table__data table--striped">
+                                <thead class="table__head">
+                                    <tr class="table__header-row">
+                                        <th class="table__header-cell">"SKU"</th>
+                                        <th class="table__header-cell">"Название"</th>
+                                        <th class="table__header-cell">"Строк"</th>
+                                        <th class="table__header-cell">"Документов"</th>
+                                        <th class="table__header-cell">"Последняя операция"</th>
+                                        <th class="table__header-cell">"Подключение"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {data.rows.into_iter().map(|row| {
+                                        view! {
+                                            <tr class="table__row">
+                                                <td class="table__cell">{row.sku}</td>
+                                                <td class="table__cell">{row.name}</td>
+                                                <td class="table__cell table__cell--right">{row.items}</td>
+                                                <td class="table__cell table__cell--right">{row.documents}</td>
+                                                <td class="table__cell">{row.last_operation_date.unwrap_or_default()}</td>
+                                                <td class="table__cell">{row.connection_id}</td>
+                                            </tr>
+                                        }
+                                    }).collect_view()}
+                                </tbody>
+                            </table>
+                        </div>
+                    }
+                    .into_any()
+                })
+            }}
+        </section>
+    }
+}
@@ -0,0 +1,21 @@
+-- Seed: компактизация проекций p900/p904 (task025) — раз в неделю, ночь на воскресенье.
+-- Время cron в UTC (МСК = UTC+3): '0 0 0 * * SUN' → 03:00 МСК, вне окна импортов.
+-- Создаётся выключенной и в режиме dry_run: администратор проверяет отчёт первого прогона
+-- и только затем переключает dry_run = 0.
+
+INSERT OR IGNORE INTO sys_tasks (
+    id, code, description, task_type, schedule_cron, config_json,
+    is_enabled, next_run_at, created_at, updated_at, is_deleted
+) VALUES (
+    'c0250025-0000-4025-b025-000000000025',
+    'task025-projection-compaction',
+    'Обслуживание: очистка мёртвых строк p900/p904 + VACUUM (вс 03:00 МСК).',
+    'task025_projection_compaction',
+    '0 0 0 * * SUN',
+    '{"dry_run":1,"reindex":0,"vacuum":1}',
+    0,
+    NULL,
+    datetime('now'),
+    datetime('now'),
+    0
+);
@@ -0,0 +1,37 @@
+-- Складские алерты по товарам WB (task026): «запаса меньше чем на N дней» и
+-- «нет продаж M дней при ненулевом остатке». Таблица — результат последнего расчёта,
+-- целиком перезаписывается задачей; список a007 читает её для бейджей.
+CREATE TABLE IF NOT EXISTS a007_stock_alerts (
+    connection_mp_ref TEXT    NOT NULL,
+    marketplace_sku   TEXT    NOT NULL,   -- для WB = nm_id
+    kind              TEXT    NOT NULL,   -- 'low_cover' | 'stale_stock'
+    vendor_code       TEXT    NOT NULL DEFAULT '',
+    title             TEXT    NOT NULL DEFAULT '',
+    stock             INTEGER NOT NULL,   -- склады WB + продавца, шт.
+    days_of_cover     REAL,               -- NULL для stale_stock
+    last_sale_date    TEXT,               -- YYYY-MM-DD, NULL если продаж не было
+    computed_at       TEXT    NOT NULL,   -- UTC ISO8601
+    PRIMARY KEY (connection_mp_ref, marketplace_sku, kind)
+);
+
+CREATE INDEX IF NOT EXISTS idx_a007_stock_alerts_sku ON a007_stock_alerts(marketplace_sku);
+
+-- Seed: расчёт алертов раз в сутки после ночного снимка a037 (task020).
+-- Время cron в UTC (МСК = UTC+3): '0 0 5 * * *' → 08:00 МСК.
+-- Создаётся выключенной: получатели писем задаются в конфиге перед включением.
+INSERT OR IGNORE INTO sys_tasks (
+    id, code, description, task_type, schedule_cron, config_json,
+    is_enabled, next_run_at, created_at, updated_at, is_deleted
+) VALUES (
+    'c0260026-0000-4026-b026-000000000026',
+    'task026-stock-alerts',
+    'Складские алерты WB: запас в днях и залежалый товар (ежедневно 08:00 МСК).',
+    'task026_stock_alerts',
+    '0 0 5 * * *',
+    '{"low_cover_days":14,"stale_days":60,"velocity_days":28,"recipients":""}',
+    0,
+    NULL,
+    datetime('now'),
+    datetime('now'),
+    0
+);
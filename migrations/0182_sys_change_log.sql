@@ -0,0 +1,14 @@
+-- Лента изменений (CDC) для внешних потребителей (корпоративное DWH): упорядоченные
+-- события «создан / изменён / проведён / распроведён» по агрегатам и проекциям.
+-- Пишется в той же транзакции, что и сами данные; offset монотонен, т.к. SQLite
+-- сериализует запись. Отдаётся через GET /api/ext/v1/changes?after=<offset>.
+CREATE TABLE IF NOT EXISTS sys_change_log (
+    change_offset INTEGER PRIMARY KEY AUTOINCREMENT,
+    ts            TEXT    NOT NULL,   -- UTC ISO8601
+    entity_kind   TEXT    NOT NULL,   -- 'aggregate' | 'projection'
+    entity_type   TEXT    NOT NULL,   -- 'a012_wb_sales', 'p904_sales_data', ...
+    entity_id     TEXT    NOT NULL,   -- id агрегата; для проекций — registrator_ref
+    op            TEXT    NOT NULL    -- 'created' | 'updated' | 'posted' | 'unposted'
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_change_log_ts ON sys_change_log(ts);
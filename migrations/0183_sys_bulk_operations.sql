@@ -0,0 +1,19 @@
+-- Журнал массовых разрушающих операций (пакетная отмена проведения) для отмены
+-- в течение настраиваемого окна (sys_settings.bulk_undo_window_minutes).
+-- snapshot_json хранит прежнее состояние: id документов, которые были проведены
+-- до операции; отмена перепроводит именно их.
+CREATE TABLE IF NOT EXISTS sys_bulk_operations (
+    id            TEXT    PRIMARY KEY,
+    op_kind       TEXT    NOT NULL,   -- 'unpost'
+    entity_type   TEXT    NOT NULL,   -- 'a012_wb_sales' | 'a013_ym_order' | 'a016_ym_returns'
+    created_at    TEXT    NOT NULL,   -- UTC ISO8601
+    created_by    TEXT,               -- id пользователя
+    item_count    INTEGER NOT NULL,
+    snapshot_json TEXT    NOT NULL,   -- JSON-массив id документов
+    status        TEXT    NOT NULL DEFAULT 'done', -- 'done' | 'undoing' | 'undone' | 'undo_partial'
+    undone_at     TEXT,
+    undone_by     TEXT,
+    undo_error    TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_bulk_operations_created_at ON sys_bulk_operations(created_at);
@@ -0,0 +1,19 @@
+-- Архив проекций по месяцам: строки старше границы архивации
+-- (sys_settings.projection_archive_boundary, 'YYYY-MM') переносятся из рабочих
+-- таблиц в *_archive, чтобы запросы за «горячие» месяцы не сканировали годы истории.
+-- Репозитории прозрачно добавляют архив (UNION ALL), если период запроса начинается
+-- раньше границы (см. shared::data::projection_archive).
+--
+-- Набор и порядок колонок копируются из рабочих таблиц (UNION ALL через SELECT *),
+-- поэтому любая миграция, добавляющая колонку в p900/p904, должна добавить её и в архив.
+CREATE TABLE IF NOT EXISTS p900_sales_register_archive AS
+    SELECT * FROM p900_sales_register WHERE 0;
+
+CREATE INDEX IF NOT EXISTS idx_p900_archive_sale_date ON p900_sales_register_archive(sale_date);
+CREATE INDEX IF NOT EXISTS idx_p900_archive_registrator_ref ON p900_sales_register_archive(registrator_ref);
+
+CREATE TABLE IF NOT EXISTS p904_sales_data_archive AS
+    SELECT * FROM p904_sales_data WHERE 0;
+
+CREATE INDEX IF NOT EXISTS idx_p904_archive_date ON p904_sales_data_archive(date);
+CREATE INDEX IF NOT EXISTS idx_p904_archive_registrator ON p904_sales_data_archive(registrator_ref);
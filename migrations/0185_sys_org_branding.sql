@@ -0,0 +1,13 @@
+-- Брендирование по организациям (a002): название продукта, акцентный цвет и логотип
+-- для клиентов, которым инструмент хостится под их собственным видом.
+-- Какое брендирование показать, решает хост запроса (hosts — список через запятую);
+-- если хост не совпал ни с одной строкой, берётся строка с is_default = 1.
+CREATE TABLE IF NOT EXISTS sys_org_branding (
+    organization_id TEXT    PRIMARY KEY,  -- a002_organization.id
+    product_name    TEXT    NOT NULL,
+    accent_color    TEXT,                 -- '#rrggbb'
+    logo_url        TEXT,                 -- https://… или data:image/…
+    hosts           TEXT    NOT NULL DEFAULT '',
+    is_default      INTEGER NOT NULL DEFAULT 0,
+    updated_at      TEXT    NOT NULL      -- UTC ISO8601
+);
@@ -0,0 +1,14 @@
+-- Внешние учётные записи пользователей (вход через корпоративный OIDC).
+-- Пара (issuer, subject) однозначно определяет пользователя у провайдера;
+-- при первом входе она связывается с существующим пользователем по email/логину
+-- или с созданным автоматически.
+CREATE TABLE IF NOT EXISTS sys_user_identities (
+    issuer        TEXT NOT NULL,
+    subject       TEXT NOT NULL,         -- claim `sub` ID-токена
+    user_id       TEXT NOT NULL,         -- sys_users.id
+    created_at    TEXT NOT NULL,         -- UTC ISO8601
+    last_login_at TEXT,
+    PRIMARY KEY (issuer, subject)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_user_identities_user ON sys_user_identities (user_id);
@@ -0,0 +1,31 @@
+-- Персональные настройки уведомлений: какие события по каким каналам получает
+-- пользователь. Отсутствующая ячейка — значение по умолчанию (только в приложении).
+CREATE TABLE IF NOT EXISTS sys_notification_preferences (
+    user_id    TEXT    NOT NULL,          -- sys_users.id
+    event      TEXT    NOT NULL,          -- import_failed | document_assigned | alert_fired
+    channel    TEXT    NOT NULL,          -- in_app | email | telegram
+    enabled    INTEGER NOT NULL,
+    updated_at TEXT    NOT NULL,          -- UTC ISO8601
+    PRIMARY KEY (user_id, event, channel)
+);
+
+-- Адреса доставки, которых нет в профиле пользователя
+CREATE TABLE IF NOT EXISTS sys_notification_contacts (
+    user_id          TEXT PRIMARY KEY,    -- sys_users.id
+    telegram_chat_id TEXT,
+    updated_at       TEXT NOT NULL
+);
+
+-- Входящие уведомления канала «в приложении» (колокольчик в шапке)
+CREATE TABLE IF NOT EXISTS sys_notifications (
+    id         TEXT PRIMARY KEY,
+    user_id    TEXT NOT NULL,
+    event      TEXT NOT NULL,
+    title      TEXT NOT NULL,
+    body       TEXT NOT NULL,
+    tab_key    TEXT,
+    created_at TEXT NOT NULL,
+    read_at    TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_notifications_user ON sys_notifications (user_id, created_at);
@@ -0,0 +1,9 @@
+-- Контрольные точки долгих загрузок: последний полностью обработанный день диапазона.
+-- Перезапуск с тем же диапазоном продолжает со следующего дня, а не с начала.
+CREATE TABLE IF NOT EXISTS sys_import_checkpoints (
+    scope             TEXT PRIMARY KEY,   -- например p903_wb_finance_report:<connection_id>
+    range_from        TEXT NOT NULL,      -- YYYY-MM-DD, начало диапазона загрузки
+    range_to          TEXT NOT NULL,      -- YYYY-MM-DD, конец диапазона загрузки
+    completed_through TEXT NOT NULL,      -- YYYY-MM-DD, последний обработанный день
+    updated_at        TEXT NOT NULL       -- UTC ISO8601
+);
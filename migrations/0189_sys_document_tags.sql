@@ -0,0 +1,12 @@
+-- Метки документов, проставляемые пакетно со страницы «История операций»
+-- (вставка списка SRID / номеров отправлений → «Пометить»).
+CREATE TABLE IF NOT EXISTS sys_document_tags (
+    entity_type TEXT NOT NULL,            -- a012_wb_sales | a015_wb_orders | a010_ozon_fbs_posting | a011_ozon_fbo_posting
+    entity_id   TEXT NOT NULL,            -- id документа
+    tag         TEXT NOT NULL,
+    created_at  TEXT NOT NULL,            -- UTC ISO8601
+    created_by  TEXT,                     -- sys_users.id
+    PRIMARY KEY (entity_type, entity_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_document_tags_tag ON sys_document_tags(tag);
@@ -0,0 +1,20 @@
+-- Фоновые выгрузки («Мои выгрузки»): тяжёлый экспорт формируется задачей на сервере,
+-- файл сохраняется в S3 (sys_s3_files, категория 'exports') и удаляется по expires_at.
+CREATE TABLE IF NOT EXISTS sys_export_jobs (
+    id          TEXT    PRIMARY KEY,
+    user_id     TEXT    NOT NULL,   -- владелец; видит и скачивает только он
+    kind        TEXT    NOT NULL,   -- 'p903_wb_finance_report'
+    title       TEXT    NOT NULL,
+    status      TEXT    NOT NULL DEFAULT 'queued', -- 'queued' | 'running' | 'done' | 'failed' | 'expired'
+    file_id     TEXT,               -- sys_s3_files.id
+    filename    TEXT,
+    size_bytes  INTEGER,
+    row_count   INTEGER,
+    error       TEXT,
+    created_at  TEXT    NOT NULL,   -- UTC ISO8601
+    finished_at TEXT,
+    expires_at  TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_export_jobs_user ON sys_export_jobs(user_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_sys_export_jobs_expires ON sys_export_jobs(status, expires_at);
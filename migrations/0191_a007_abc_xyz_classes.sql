@@ -0,0 +1,38 @@
+-- ABC/XYZ-классы товаров маркетплейсов по кварталам (task027).
+-- ABC — доля в выручке квартала, XYZ — коэффициент вариации недельных продаж.
+-- Расчёт квартала целиком заменяет его строки; списки и дашборды берут последний квартал.
+CREATE TABLE IF NOT EXISTS a007_abc_xyz_classes (
+    marketplace_product_ref TEXT    NOT NULL,   -- a007.id
+    quarter                 TEXT    NOT NULL,   -- 'YYYY-Qn'
+    abc_class               TEXT    NOT NULL,   -- 'A' | 'B' | 'C'
+    xyz_class               TEXT    NOT NULL,   -- 'X' | 'Y' | 'Z'
+    revenue                 REAL    NOT NULL,   -- p900.amount_line за квартал
+    revenue_share           REAL    NOT NULL,   -- % выручки квартала
+    qty                     REAL    NOT NULL,   -- продано штук за квартал
+    demand_cv               REAL,               -- %, NULL — продаж в штуках не было
+    computed_at             TEXT    NOT NULL,   -- UTC ISO8601
+    PRIMARY KEY (marketplace_product_ref, quarter)
+);
+
+CREATE INDEX IF NOT EXISTS idx_a007_abc_xyz_quarter_class
+    ON a007_abc_xyz_classes(quarter, abc_class, xyz_class);
+
+-- Seed: пересчёт двух последних завершённых кварталов 3-го числа каждого месяца —
+-- поздние финансовые данные маркетплейсов успевают попасть в классы.
+-- Время cron в UTC (МСК = UTC+3): '0 0 3 3 * *' → 06:00 МСК.
+INSERT OR IGNORE INTO sys_tasks (
+    id, code, description, task_type, schedule_cron, config_json,
+    is_enabled, next_run_at, created_at, updated_at, is_deleted
+) VALUES (
+    'c0270027-0000-4027-b027-000000000027',
+    'task027-abc-xyz-classification',
+    'ABC/XYZ-классификация товаров по кварталам (3-го числа, 06:00 МСК).',
+    'task027_abc_xyz_classification',
+    '0 0 3 3 * *',
+    '{"quarters":2,"a_share":80,"b_share":95,"x_max_cv":25,"y_max_cv":50}',
+    0,
+    NULL,
+    datetime('now'),
+    datetime('now'),
+    0
+);
@@ -0,0 +1,44 @@
+-- Аномалии дневных продаж (task028): сезонный z-score по дню недели на ряде p900.
+-- marketplace_product_ref = '' — итог продаж кабинета за день.
+-- Пересчёт дня обновляет цифры; пояснение пользователя (разбор) сохраняется.
+CREATE TABLE IF NOT EXISTS a007_sales_anomalies (
+    id                      TEXT    PRIMARY KEY,
+    connection_mp_ref       TEXT    NOT NULL,
+    marketplace_product_ref TEXT    NOT NULL DEFAULT '',   -- a007.id или ''
+    sale_date               TEXT    NOT NULL,              -- YYYY-MM-DD
+    direction               TEXT    NOT NULL,              -- 'spike' | 'drop'
+    actual_qty              REAL    NOT NULL,
+    expected_qty            REAL    NOT NULL,              -- среднее того же дня недели
+    std_dev                 REAL    NOT NULL,
+    z_score                 REAL    NOT NULL,
+    actual_revenue          REAL    NOT NULL,
+    expected_revenue        REAL    NOT NULL,
+    detected_at             TEXT    NOT NULL,              -- UTC ISO8601
+    updated_at              TEXT    NOT NULL,
+    explanation             TEXT,                          -- NULL — не разобрана
+    explained_by            TEXT,                          -- имя пользователя
+    explained_at            TEXT,
+    UNIQUE (connection_mp_ref, marketplace_product_ref, sale_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_a007_sales_anomalies_date
+    ON a007_sales_anomalies(sale_date);
+
+-- Seed: ежедневная проверка последних дней после ночного обновления продаж.
+-- Время cron в UTC (МСК = UTC+3): '0 30 5 * * *' → 08:30 МСК.
+INSERT OR IGNORE INTO sys_tasks (
+    id, code, description, task_type, schedule_cron, config_json,
+    is_enabled, next_run_at, created_at, updated_at, is_deleted
+) VALUES (
+    'c0280028-0000-4028-b028-000000000028',
+    'task028-sales-anomalies',
+    'Аномалии дневных продаж по товарам и кабинетам (ежедневно 08:30 МСК).',
+    'task028_sales_anomalies',
+    '0 30 5 * * *',
+    '{"check_days":3,"lag_days":1,"history_weeks":8,"z_threshold":3,"min_qty_delta":5}',
+    0,
+    NULL,
+    datetime('now'),
+    datetime('now'),
+    0
+);
@@ -0,0 +1,37 @@
+-- Недельный прогноз спроса по товарам (task029). Каждый запуск хранится отдельно
+-- (made_on — понедельник недели расчёта), чтобы сравнивать факт с прогнозом,
+-- сделанным за N недель до него (D412). Повторный запуск в ту же неделю заменяет её.
+CREATE TABLE IF NOT EXISTS a007_demand_forecasts (
+    marketplace_product_ref TEXT    NOT NULL,              -- a007.id
+    made_on                 TEXT    NOT NULL,              -- YYYY-MM-DD, понедельник
+    week_start              TEXT    NOT NULL,              -- YYYY-MM-DD, понедельник
+    forecast_qty            REAL    NOT NULL,              -- шт. за неделю
+    method                  TEXT    NOT NULL,              -- ForecastMethod::code()
+    computed_at             TEXT    NOT NULL,              -- UTC ISO8601
+    PRIMARY KEY (marketplace_product_ref, made_on, week_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_a007_demand_forecasts_made_on
+    ON a007_demand_forecasts(made_on);
+
+CREATE INDEX IF NOT EXISTS idx_a007_demand_forecasts_week
+    ON a007_demand_forecasts(week_start);
+
+-- Seed: еженедельный расчёт в понедельник, после закрытия недели продаж.
+-- Время cron в UTC (МСК = UTC+3): '0 0 4 * * MON' → 07:00 МСК.
+INSERT OR IGNORE INTO sys_tasks (
+    id, code, description, task_type, schedule_cron, config_json,
+    is_enabled, next_run_at, created_at, updated_at, is_deleted
+) VALUES (
+    'c0290029-0000-4029-b029-000000000029',
+    'task029-demand-forecast',
+    'Недельный прогноз спроса по товарам (понедельник 07:00 МСК).',
+    'task029_demand_forecast',
+    '0 0 4 * * MON',
+    '{"history_weeks":104,"horizon_weeks":12,"alpha_percent":30}',
+    0,
+    NULL,
+    datetime('now'),
+    datetime('now'),
+    0
+);
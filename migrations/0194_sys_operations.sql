@@ -0,0 +1,20 @@
+-- Пакетные операции (POST /api/operations): запрос сразу получает operation_id,
+-- операция выполняется в фоне, прогресс и итог читаются опросом GET /api/operations/{id}.
+CREATE TABLE IF NOT EXISTS sys_operations (
+    id           TEXT    PRIMARY KEY,
+    user_id      TEXT    NOT NULL,   -- инициатор; видит операцию только он
+    kind         TEXT    NOT NULL,   -- OperationRequest::kind(): 'post_documents' | 'unpost_documents' | ...
+    title        TEXT    NOT NULL,
+    request_json TEXT    NOT NULL,   -- исходный OperationRequest
+    status       TEXT    NOT NULL DEFAULT 'queued', -- 'queued' | 'running' | 'completed' | 'failed'
+    processed    INTEGER NOT NULL DEFAULT 0,
+    total        INTEGER NOT NULL DEFAULT 0,
+    result_json  TEXT,               -- BulkActionResultDto
+    error        TEXT,               -- операция не выполнилась целиком
+    created_at   TEXT    NOT NULL,   -- UTC ISO8601
+    started_at   TEXT,
+    finished_at  TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_operations_user ON sys_operations(user_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_sys_operations_status ON sys_operations(status);
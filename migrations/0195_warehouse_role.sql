@@ -0,0 +1,9 @@
+-- =============================================================================
+-- Migration 0195: Warehouse primary role
+-- =============================================================================
+-- Adds: built-in role 'warehouse' (склад) — складские документы без доступа
+--       к финансовым показателям (scope view_finance)
+-- =============================================================================
+
+INSERT INTO sys_roles (code, name, is_system) VALUES
+    ('warehouse', 'Склад', 1);
@@ -0,0 +1,17 @@
+-- Отложенное проведение документов (POST /api/scheduled-posts): сервер проводит документ,
+-- когда наступает post_at. На документ — не более одного ожидающего задания.
+CREATE TABLE IF NOT EXISTS sys_scheduled_posts (
+    id          TEXT    PRIMARY KEY,
+    entity_type TEXT    NOT NULL,   -- 'a012_wb_sales' | 'a013_ym_order' | 'a016_ym_returns'
+    document_id TEXT    NOT NULL,
+    post_at     TEXT    NOT NULL,   -- UTC ISO8601
+    status      TEXT    NOT NULL DEFAULT 'pending', -- 'pending' | 'done' | 'cancelled' | 'failed'
+    error       TEXT,
+    created_by  TEXT    NOT NULL,   -- логин
+    created_at  TEXT    NOT NULL,   -- UTC ISO8601
+    finished_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_scheduled_posts_due ON sys_scheduled_posts(status, post_at);
+CREATE INDEX IF NOT EXISTS idx_sys_scheduled_posts_document
+    ON sys_scheduled_posts(entity_type, document_id, status);
@@ -0,0 +1,8 @@
+-- compat: expand
+-- Флаги совместимости применённых миграций (заполняет migration_runner после применения).
+-- 'expand' — обратно совместимый шаг: предыдущая сборка может стартовать на этой схеме;
+-- 'contract' — несовместимый. См. shared/data/online_migrations.rs.
+CREATE TABLE IF NOT EXISTS sys_migration_compat (
+    version INTEGER PRIMARY KEY,
+    compat  TEXT    NOT NULL   -- 'expand' | 'contract'
+);
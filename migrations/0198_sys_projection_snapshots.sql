@@ -0,0 +1,25 @@
+-- compat: expand
+-- Снимки итогов p900 (по месяцу, маркетплейсу и SKU) на момент создания: сравнение
+-- двух снимков или снимка с текущими данными показывает, что изменилось в уже
+-- отчитанном периоде. См. shared/data/projection_snapshots.rs.
+CREATE TABLE IF NOT EXISTS sys_projection_snapshots (
+    id           TEXT    PRIMARY KEY,
+    label        TEXT    NOT NULL,
+    period_from  TEXT    NOT NULL,   -- 'YYYY-MM'
+    period_to    TEXT    NOT NULL,   -- 'YYYY-MM' включительно
+    row_count    INTEGER NOT NULL DEFAULT 0,
+    revenue      REAL    NOT NULL DEFAULT 0,
+    created_by   TEXT,               -- логин
+    created_at   TEXT    NOT NULL    -- UTC ISO8601
+);
+
+CREATE TABLE IF NOT EXISTS sys_projection_snapshot_rows (
+    snapshot_id  TEXT    NOT NULL,
+    period       TEXT    NOT NULL,   -- 'YYYY-MM'
+    marketplace  TEXT    NOT NULL,
+    sku          TEXT    NOT NULL,   -- seller_sku, иначе mp_item_id
+    qty          REAL    NOT NULL DEFAULT 0,
+    revenue      REAL    NOT NULL DEFAULT 0,
+    cost         REAL    NOT NULL DEFAULT 0,
+    PRIMARY KEY (snapshot_id, period, marketplace, sku)
+);
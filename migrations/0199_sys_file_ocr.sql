@@ -0,0 +1,50 @@
+-- compat: expand
+-- Распознавание текста вложений (акты, счета) для полнотекстового поиска.
+-- Файл S3 может быть привязан к документу (document_type + document_id): поиск по
+-- тексту вложения находит документ. Текст извлекает task030 (см. system/s3/ocr.rs).
+
+ALTER TABLE sys_files_s3 ADD COLUMN document_type TEXT;   -- 'a023_purchase_of_goods' | ...
+ALTER TABLE sys_files_s3 ADD COLUMN document_id TEXT;     -- id документа
+
+CREATE INDEX IF NOT EXISTS idx_sys_files_s3_document
+    ON sys_files_s3 (document_type, document_id)
+    WHERE is_deleted = 0;
+
+-- Состояние распознавания: одна строка на обработанный файл.
+CREATE TABLE IF NOT EXISTS sys_file_ocr (
+    file_id      TEXT    PRIMARY KEY,   -- sys_files_s3.id
+    status       TEXT    NOT NULL,      -- 'done' | 'skipped' | 'failed'
+    engine       TEXT,                  -- OcrEngine::code()
+    text_length  INTEGER NOT NULL DEFAULT 0,
+    error        TEXT,
+    attempts     INTEGER NOT NULL DEFAULT 1,
+    processed_at TEXT    NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_file_ocr_status
+    ON sys_file_ocr (status);
+
+-- Извлечённый текст. file_id не индексируется, поиск — только по content.
+CREATE VIRTUAL TABLE IF NOT EXISTS sys_file_ocr_fts USING fts5(
+    file_id UNINDEXED,
+    content,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Seed: распознавание новых вложений каждые 15 минут (выключено до настройки [ocr]).
+INSERT OR IGNORE INTO sys_tasks (
+    id, code, description, task_type, schedule_cron, config_json,
+    is_enabled, next_run_at, created_at, updated_at, is_deleted
+) VALUES (
+    'c0300030-0000-4030-b030-000000000030',
+    'task030-attachment-ocr',
+    'Распознавание текста вложений для поиска (каждые 15 минут).',
+    'task030_attachment_ocr',
+    '0 */15 * * * *',
+    '{"batch_size":20,"retry_failed":0}',
+    0,
+    NULL,
+    datetime('now'),
+    datetime('now'),
+    0
+);
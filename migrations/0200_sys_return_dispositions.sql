@@ -0,0 +1,46 @@
+-- compat: expand
+-- Решения по физическим возвратам: на склад, утилизация, возврат поставщику.
+-- Фото — вложения sys_files_s3, привязанные к документу возврата (document_type/document_id).
+-- На документ — одно решение; утверждение пишет движения в p917_return_stock_ledger.
+
+CREATE TABLE IF NOT EXISTS sys_return_dispositions (
+    id            TEXT    PRIMARY KEY,
+    entity_type   TEXT    NOT NULL,   -- 'a009_ozon_returns' | 'a016_ym_returns' | 'a032_wb_returns_claims'
+    document_id   TEXT    NOT NULL,
+    decision      TEXT    NOT NULL,   -- 'return_to_stock' | 'dispose' | 'send_to_supplier'
+    status        TEXT    NOT NULL DEFAULT 'draft', -- 'draft' | 'pending_approval' | 'approved' | 'rejected'
+    comment       TEXT,
+    created_by    TEXT    NOT NULL,   -- логин
+    created_at    TEXT    NOT NULL,   -- UTC ISO8601
+    submitted_at  TEXT,
+    decided_by    TEXT,               -- логин утвердившего/отклонившего
+    decided_at    TEXT,
+    reject_reason TEXT
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_sys_return_dispositions_document
+    ON sys_return_dispositions (entity_type, document_id);
+CREATE INDEX IF NOT EXISTS idx_sys_return_dispositions_status
+    ON sys_return_dispositions (status);
+
+-- Движения возвращённого товара по утверждённым решениям (регистратор — решение).
+CREATE TABLE IF NOT EXISTS p917_return_stock_ledger (
+    id              TEXT    PRIMARY KEY,
+    entry_date      TEXT    NOT NULL,   -- YYYY-MM-DD, дата утверждения
+    decision        TEXT    NOT NULL,   -- тег движения = код решения
+    entity_type     TEXT    NOT NULL,
+    document_id     TEXT    NOT NULL,
+    line_no         INTEGER NOT NULL,
+    organization_id TEXT    NOT NULL,
+    article         TEXT    NOT NULL,   -- SKU Ozon / shopSku ЯМ / nmId WB
+    product_name    TEXT    NOT NULL,
+    quantity        INTEGER NOT NULL,
+    stock_delta     INTEGER NOT NULL,   -- +quantity при возврате на склад, иначе 0
+    registrator_ref TEXT    NOT NULL,   -- sys_return_dispositions.id
+    created_at      TEXT    NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_p917_return_stock_ledger_registrator
+    ON p917_return_stock_ledger (registrator_ref);
+CREATE INDEX IF NOT EXISTS idx_p917_return_stock_ledger_date_decision
+    ON p917_return_stock_ledger (entry_date, decision);
@@ -0,0 +1,21 @@
+-- compat: expand
+-- Квоты организаций-клиентов (a002): запросов к API в сутки и строк выгрузок в месяц.
+-- Организация запроса определяется по хосту — так же, как брендирование (sys_org_branding).
+-- NULL в лимите — без ограничения.
+CREATE TABLE IF NOT EXISTS sys_org_quotas (
+    organization_id       TEXT    PRIMARY KEY,  -- a002_organization.id
+    requests_per_day      INTEGER,
+    export_rows_per_month INTEGER,
+    updated_at            TEXT    NOT NULL,     -- UTC ISO8601
+    updated_by            TEXT    NOT NULL      -- логин
+);
+
+-- Счётчики использования по периодам: 'requests' — за сутки (YYYY-MM-DD, UTC),
+-- 'export_rows' — за месяц (YYYY-MM). Пишутся из памяти процесса раз в несколько секунд.
+CREATE TABLE IF NOT EXISTS sys_org_usage (
+    organization_id TEXT    NOT NULL,
+    metric          TEXT    NOT NULL,   -- 'requests' | 'export_rows'
+    period          TEXT    NOT NULL,
+    value           INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (organization_id, metric, period)
+);
@@ -0,0 +1,10 @@
+-- compat: expand
+-- Шаблоны поля description документов, загружаемых импортом (a012, a015).
+-- Плейсхолдеры {supplier_article}, {sale_dt} и т.п. — список в contracts::system::description_templates.
+-- Нет строки — импорт пишет описание по умолчанию.
+CREATE TABLE IF NOT EXISTS sys_description_templates (
+    entity_type TEXT PRIMARY KEY,   -- 'a012_wb_sales' | 'a015_wb_orders'
+    template    TEXT NOT NULL,
+    updated_at  TEXT NOT NULL,      -- UTC ISO8601
+    updated_by  TEXT NOT NULL       -- логин
+);
@@ -0,0 +1,16 @@
+-- compat: expand
+-- Seed: атомарное OZON-задание — возвраты (task031) в a009_ozon_returns.
+-- ВНИМАНИЕ: Перед включением замените connection_id на реальный UUID OZON-кабинета
+--           из справочника a006_connection_mp. Задание создаётся отключённым (is_enabled = 0).
+
+-- task031: Возвраты OZON (раз в день, watermark + догон)
+INSERT OR IGNORE INTO sys_tasks (id, code, description, task_type, schedule_cron, config_json, is_enabled, created_at, updated_at, is_deleted)
+VALUES (
+    'a1b2c3d4-e5f6-7890-abcd-ef1234567831',
+    'task031-ozon-returns',
+    'OZON Возвраты — Seller API /v1/returns/list (раз в день, watermark + догон). Замените connection_id на UUID OZON-кабинета.',
+    'task031_ozon_returns',
+    '0 30 7 * * *',
+    '{"connection_id":"REPLACE_WITH_OZON_CONNECTION_ID","work_start_date":"2026-01-01","overlap_days":2,"chunk_days":14}',
+    0, datetime('now'), datetime('now'), 0
+);
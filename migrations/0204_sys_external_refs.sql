@@ -0,0 +1,20 @@
+-- compat: expand
+-- Внешние ссылки документов: номер претензии WB, номер документа ERP и т.п.
+-- Ссылка ищется глобально по ключу (LIKE), документ — по entity_type + document_id.
+
+CREATE TABLE IF NOT EXISTS sys_external_refs (
+    id          TEXT PRIMARY KEY,
+    entity_type TEXT NOT NULL,   -- 'a012_wb_sales' | 'a015_wb_orders' | 'a032_wb_returns_claims'
+    document_id TEXT NOT NULL,
+    system      TEXT NOT NULL,   -- «Претензия WB», «1С», ...
+    ref_key     TEXT NOT NULL,
+    url         TEXT,
+    created_by  TEXT NOT NULL,   -- логин
+    created_at  TEXT NOT NULL,   -- UTC ISO8601
+    updated_at  TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_sys_external_refs_document_key
+    ON sys_external_refs (entity_type, document_id, system, ref_key);
+CREATE INDEX IF NOT EXISTS idx_sys_external_refs_key
+    ON sys_external_refs (ref_key);
@@ -0,0 +1,24 @@
+-- compat: expand
+-- p900: склад продажи для фильтра реестра. Заполняется только для WB (a012),
+-- у остальных источников склада в документе нет.
+-- Колонка добавляется и в архив — UNION ALL через SELECT * (см. 0184).
+ALTER TABLE p900_sales_register ADD COLUMN warehouse_name TEXT;
+ALTER TABLE p900_sales_register_archive ADD COLUMN warehouse_name TEXT;
+
+UPDATE p900_sales_register
+SET warehouse_name = (
+    SELECT json_extract(s.warehouse_json, '$.warehouse_name')
+    FROM a012_wb_sales s
+    WHERE s.id = p900_sales_register.registrator_ref
+)
+WHERE document_type = 'WB_Sales';
+
+UPDATE p900_sales_register_archive
+SET warehouse_name = (
+    SELECT json_extract(s.warehouse_json, '$.warehouse_name')
+    FROM a012_wb_sales s
+    WHERE s.id = p900_sales_register_archive.registrator_ref
+)
+WHERE document_type = 'WB_Sales';
+
+CREATE INDEX IF NOT EXISTS idx_p900_warehouse_name ON p900_sales_register(warehouse_name);
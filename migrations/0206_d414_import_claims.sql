@@ -0,0 +1,35 @@
+-- compat: expand
+-- Сводки маркетплейсов по дням для отчёта покрытия импорта (D414).
+-- task032 сохраняет, сколько документов и на какую сумму маркетплейс заявил за день;
+-- импортированная сторона считается отчётом на лету из агрегатов (a012, a014).
+CREATE TABLE IF NOT EXISTS d414_import_claims (
+    connection_mp_ref TEXT    NOT NULL,              -- a006.id
+    source            TEXT    NOT NULL,              -- CoverageSource::code()
+    day               TEXT    NOT NULL,              -- YYYY-MM-DD
+    claimed_count     INTEGER NOT NULL,
+    claimed_amount    REAL,                          -- NULL, если API суммы не даёт
+    checked_at        TEXT    NOT NULL,              -- UTC ISO8601
+    PRIMARY KEY (connection_mp_ref, source, day)
+);
+
+CREATE INDEX IF NOT EXISTS idx_d414_import_claims_day
+    ON d414_import_claims(day);
+
+-- Seed: ежедневная сверка после ночных импортов.
+-- Время cron в UTC (МСК = UTC+3): '0 0 6 * * *' → 09:00 МСК.
+INSERT OR IGNORE INTO sys_tasks (
+    id, code, description, task_type, schedule_cron, config_json,
+    is_enabled, next_run_at, created_at, updated_at, is_deleted
+) VALUES (
+    'c0320032-0000-4032-b032-000000000032',
+    'task032-import-coverage',
+    'Сверка покрытия импорта: сводки WB/Ozon за последние дни против загруженных документов (09:00 МСК).',
+    'task032_import_coverage',
+    '0 0 6 * * *',
+    '{"check_days":7,"lag_days":1}',
+    0,
+    NULL,
+    datetime('now'),
+    datetime('now'),
+    0
+);
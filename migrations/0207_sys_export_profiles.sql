@@ -0,0 +1,25 @@
+-- compat: expand
+-- Профили выгрузок: сохранённый отбор документов (метки, кабинет, период),
+-- набор колонок и формат файла. Запуск — по кнопке или по cron; результат
+-- попадает в «Мои выгрузки» владельца (sys_export_jobs, kind = 'export_profile').
+CREATE TABLE IF NOT EXISTS sys_export_profiles (
+    id               TEXT PRIMARY KEY NOT NULL,
+    owner_user_id    TEXT NOT NULL,            -- sys_users.id; от его имени формируется файл
+    organization_id  TEXT,                     -- организация, в квоту которой идут строки
+    name             TEXT NOT NULL,
+    entity_type      TEXT NOT NULL,            -- a012_wb_sales | a015_wb_orders | a010_ozon_fbs_posting | a011_ozon_fbo_posting
+    filter_json      TEXT NOT NULL,            -- ExportProfileFilter
+    columns_json     TEXT NOT NULL,            -- ключи колонок в порядке вывода
+    format           TEXT NOT NULL DEFAULT 'csv', -- csv | xlsx | ndjson
+    schedule_cron    TEXT,
+    schedule_enabled INTEGER NOT NULL DEFAULT 0,
+    next_run_at      TEXT,                     -- UTC ISO8601; NULL — расписание выключено
+    last_run_at      TEXT,
+    last_job_id      TEXT,                     -- sys_export_jobs.id
+    last_error       TEXT,
+    created_at       TEXT NOT NULL,
+    updated_at       TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_export_profiles_owner ON sys_export_profiles(owner_user_id);
+CREATE INDEX IF NOT EXISTS idx_sys_export_profiles_next_run ON sys_export_profiles(next_run_at);
@@ -0,0 +1,51 @@
+-- compat: expand
+-- Габариты товаров WB из карточек (u504): объём для распределения хранения.
+CREATE TABLE IF NOT EXISTS a007_product_dimensions (
+    marketplace_product_ref TEXT PRIMARY KEY NOT NULL, -- a007.id
+    length_cm               REAL,
+    width_cm                REAL,
+    height_cm               REAL,
+    volume_liters           REAL,                      -- NULL — габариты не заполнены
+    updated_at              TEXT NOT NULL
+);
+
+-- Распределение расходов на хранение и приёмку WB по SKU.
+-- Источник — строки p910 без товара (уровень склада / поставки): одна строка
+-- p918 на SKU, получивший долю расхода. Строки источника заменяются целиком
+-- при каждом распределении, поэтому их сумма всегда равна сумме строки p910.
+-- Нераспределённый остаток (нет данных драйвера) — строка с nm_id = NULL.
+CREATE TABLE IF NOT EXISTS p918_storage_cost_allocation (
+    id                TEXT PRIMARY KEY NOT NULL,  -- source_ref:nm_id | source_ref:unallocated
+    source_ref        TEXT NOT NULL,              -- p910_mp_unlinked_turnovers.id
+    registrator_ref   TEXT NOT NULL,              -- p903_wb_finance_report.id
+    connection_mp_ref TEXT NOT NULL,
+    entry_date        TEXT NOT NULL,              -- YYYY-MM-DD
+    turnover_code     TEXT NOT NULL,              -- mp_storage | acceptance
+    source_amount     REAL NOT NULL,
+    driver            TEXT NOT NULL,              -- volume_share | units_stored | revenue_share | direct
+    basis             TEXT NOT NULL DEFAULT '',   -- на чём посчитан драйвер: дата снимка a037 / период выручки
+    nm_id             INTEGER,
+    article           TEXT NOT NULL DEFAULT '',
+    nomenclature_ref  TEXT,
+    driver_value      REAL NOT NULL DEFAULT 0,
+    driver_total      REAL NOT NULL DEFAULT 0,
+    share             REAL NOT NULL,
+    amount            REAL NOT NULL,
+    comment           TEXT,
+    created_at        TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_p918_source ON p918_storage_cost_allocation(source_ref);
+CREATE INDEX IF NOT EXISTS idx_p918_registrator ON p918_storage_cost_allocation(registrator_ref);
+CREATE INDEX IF NOT EXISTS idx_p918_nm_date ON p918_storage_cost_allocation(nm_id, entry_date);
+CREATE INDEX IF NOT EXISTS idx_p918_date ON p918_storage_cost_allocation(entry_date);
+
+INSERT OR IGNORE INTO sys_role_scope_access (role_id, access_scope_id, access_mode)
+SELECT id, 'p918_storage_cost_allocation', 'all'
+FROM sys_roles
+WHERE code = 'manager';
+
+INSERT OR IGNORE INTO sys_role_scope_access (role_id, access_scope_id, access_mode)
+SELECT id, 'p918_storage_cost_allocation', 'read'
+FROM sys_roles
+WHERE code IN ('operator', 'viewer');
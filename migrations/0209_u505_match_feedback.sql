@@ -0,0 +1,20 @@
+-- compat: expand
+-- Решения пользователей по предложениям сопоставления u505 (товар МП → номенклатура).
+-- Одна строка на пару: повторное решение заменяет прежнее. Статистика принятых /
+-- отклонённых по (brand, rule) корректирует оценку следующих предложений, а
+-- отклонённая пара больше не предлагается.
+CREATE TABLE IF NOT EXISTS u505_match_feedback (
+    id                      TEXT PRIMARY KEY NOT NULL,   -- marketplace_product_ref:nomenclature_ref
+    marketplace_product_ref TEXT NOT NULL,               -- a007.id
+    nomenclature_ref        TEXT NOT NULL,               -- a004.id
+    decision                TEXT NOT NULL,               -- accepted | rejected
+    rule                    TEXT NOT NULL,               -- exact | normalized | prefix
+    brand                   TEXT NOT NULL DEFAULT '',    -- бренд a007 в нижнем регистре ('' — без бренда)
+    score                   REAL NOT NULL DEFAULT 0,     -- оценка на момент решения
+    decided_by              TEXT,
+    decided_at              TEXT NOT NULL                -- RFC3339 UTC
+);
+
+CREATE INDEX IF NOT EXISTS idx_u505_feedback_product ON u505_match_feedback(marketplace_product_ref);
+CREATE INDEX IF NOT EXISTS idx_u505_feedback_brand_rule ON u505_match_feedback(brand, rule);
+CREATE INDEX IF NOT EXISTS idx_u505_feedback_decided_at ON u505_match_feedback(decided_at);
@@ -0,0 +1,32 @@
+-- compat: expand
+-- Журнал синхронизации статусов OZON FBS отправлений (u510, задание task033).
+-- Одна строка на каждую обнаруженную смену статуса или подстатуса документа a010.
+CREATE TABLE IF NOT EXISTS u510_fbs_status_log (
+    id            TEXT PRIMARY KEY NOT NULL,
+    connection_id TEXT NOT NULL,            -- a006.id
+    document_ref  TEXT NOT NULL,            -- a010.id
+    document_no   TEXT NOT NULL,            -- posting_number
+    old_status    TEXT NOT NULL,            -- status_raw до синхронизации
+    new_status    TEXT NOT NULL,
+    old_substatus TEXT,
+    new_substatus TEXT,
+    synced_at     TEXT NOT NULL,            -- RFC3339 UTC, общий для запуска
+    session_id    TEXT                      -- сессия sys_task_runs
+);
+
+CREATE INDEX IF NOT EXISTS idx_u510_status_log_document_no ON u510_fbs_status_log(document_no);
+CREATE INDEX IF NOT EXISTS idx_u510_status_log_synced_at ON u510_fbs_status_log(synced_at);
+
+-- Seed: task033 — синхронизация статусов FBS отправлений OZON.
+-- ВНИМАНИЕ: Перед включением замените connection_id на реальный UUID OZON-кабинета
+--           из справочника a006_connection_mp. Задание создаётся отключённым (is_enabled = 0).
+INSERT OR IGNORE INTO sys_tasks (id, code, description, task_type, schedule_cron, config_json, is_enabled, created_at, updated_at, is_deleted)
+VALUES (
+    'a1b2c3d4-e5f6-7890-abcd-ef1234567833',
+    'task033-ozon-fbs-status-sync',
+    'OZON FBS — синхронизация статусов отправлений по last_changed_status_date (каждые 2 часа). Замените connection_id на UUID OZON-кабинета.',
+    'task033_ozon_fbs_status_sync',
+    '0 15 */2 * * *',
+    '{"connection_id":"REPLACE_WITH_OZON_CONNECTION_ID","overlap_days":1,"lookback_days":60}',
+    0, datetime('now'), datetime('now'), 0
+);
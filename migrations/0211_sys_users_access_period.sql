@@ -0,0 +1,20 @@
+-- compat: expand
+-- Срок действия учётных записей (например, внешних бухгалтеров).
+-- Даты в формате YYYY-MM-DD, включительно; NULL — без ограничения.
+ALTER TABLE sys_users ADD COLUMN active_from TEXT;
+ALTER TABLE sys_users ADD COLUMN active_until TEXT;
+-- Дата окончания, о которой уже напомнили (task034); при продлении напоминание придёт снова
+ALTER TABLE sys_users ADD COLUMN expiry_reminded_until TEXT;
+
+-- Seed: task034 — отключение учётных записей с истёкшим сроком и напоминания.
+-- Внешних подключений не требует, поэтому создаётся включённым.
+INSERT OR IGNORE INTO sys_tasks (id, code, description, task_type, schedule_cron, config_json, is_enabled, created_at, updated_at, is_deleted)
+VALUES (
+    'a1b2c3d4-e5f6-7890-abcd-ef1234567834',
+    'task034-user-access-expiry',
+    'Пользователи — отключение по окончании срока доступа и напоминания за 7 дней (ежедневно в 06:00).',
+    'task034_user_access_expiry',
+    '0 0 6 * * *',
+    '{}',
+    1, datetime('now'), datetime('now'), 0
+);
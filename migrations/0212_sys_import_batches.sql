@@ -0,0 +1,19 @@
+-- compat: expand
+-- Происхождение импортированных данных: одна строка на каждый полученный от API пакет
+-- (страницу ответа). Позволяет аудитору сверить, что данные не менялись после загрузки:
+-- параметры запроса, sha256 тела ответа и число строк в нём.
+CREATE TABLE IF NOT EXISTS sys_import_batches (
+    id              TEXT PRIMARY KEY NOT NULL,
+    session_id      TEXT NOT NULL,           -- сессия импорта / sys_task_runs
+    connection_id   TEXT NOT NULL,           -- a006_connection_mp
+    aggregate_index TEXT NOT NULL,           -- a012_wb_sales, a015_wb_orders, ...
+    endpoint        TEXT NOT NULL,           -- URL без query
+    request_params  TEXT NOT NULL,           -- JSON параметров запроса (без ключей доступа)
+    response_sha256 TEXT NOT NULL,
+    response_bytes  INTEGER NOT NULL,
+    row_count       INTEGER NOT NULL,
+    fetched_at      TEXT NOT NULL            -- RFC3339 UTC
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_import_batches_session ON sys_import_batches(session_id, aggregate_index);
+CREATE INDEX IF NOT EXISTS idx_sys_import_batches_fetched_at ON sys_import_batches(fetched_at);
@@ -0,0 +1,15 @@
+-- compat: expand
+-- Seed: task035 — сверка флага is_posted со строками P900 и перепроведение
+-- документов, оставшихся проведёнными без строк (например, после аварийной остановки).
+-- Время cron в UTC (МСК = UTC+3): '0 30 4 * * *' → 07:30 МСК, после ночных импортов.
+-- Перепроводит документы, поэтому создаётся выключенным.
+INSERT OR IGNORE INTO sys_tasks (id, code, description, task_type, schedule_cron, config_json, is_enabled, created_at, updated_at, is_deleted)
+VALUES (
+    'a1b2c3d4-e5f6-7890-abcd-ef1234567835',
+    'task035-posting-consistency',
+    'Контроль — флаг проведения a009/a010/a012 против строк P900, перепроведение расхождений (07:30 МСК).',
+    'task035_posting_consistency',
+    '0 30 4 * * *',
+    '{"max_fixes":500}',
+    0, datetime('now'), datetime('now'), 0
+);
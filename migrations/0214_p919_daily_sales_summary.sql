@@ -0,0 +1,45 @@
+-- compat: expand
+-- Дневная сводка продаж по маркетплейсу и организации (a012 WB, a014 OZON).
+-- p919_daily_sales_summary_src — вклад каждого проведённого документа; при
+-- перепроведении и отмене проведения итог дня в p919_daily_sales_summary
+-- корректируется на разницу вкладов. documents_count — число документов в строке
+-- итога: строка удаляется, когда из неё ушёл последний документ.
+CREATE TABLE IF NOT EXISTS p919_daily_sales_summary_src (
+    registrator_ref  TEXT PRIMARY KEY NOT NULL,  -- id документа a012 / a014
+    registrator_type TEXT NOT NULL,              -- a012_wb_sales | a014_ozon_transactions
+    date             TEXT NOT NULL,              -- YYYY-MM-DD
+    marketplace      TEXT NOT NULL,              -- WB | OZON
+    organization_ref TEXT NOT NULL,
+    orders_count     INTEGER NOT NULL DEFAULT 0,
+    units            REAL NOT NULL DEFAULT 0,
+    gross            REAL NOT NULL DEFAULT 0,
+    commission       REAL NOT NULL DEFAULT 0,
+    net              REAL NOT NULL DEFAULT 0,
+    created_at       TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_p919_src_date ON p919_daily_sales_summary_src(date);
+
+CREATE TABLE IF NOT EXISTS p919_daily_sales_summary (
+    date             TEXT NOT NULL,
+    marketplace      TEXT NOT NULL,
+    organization_ref TEXT NOT NULL,
+    documents_count  INTEGER NOT NULL DEFAULT 0,
+    orders_count     INTEGER NOT NULL DEFAULT 0,
+    units            REAL NOT NULL DEFAULT 0,
+    gross            REAL NOT NULL DEFAULT 0,
+    commission       REAL NOT NULL DEFAULT 0,
+    net              REAL NOT NULL DEFAULT 0,
+    updated_at       TEXT NOT NULL,
+    PRIMARY KEY (date, marketplace, organization_ref)
+);
+
+INSERT OR IGNORE INTO sys_role_scope_access (role_id, access_scope_id, access_mode)
+SELECT id, 'p919_daily_sales_summary', 'all'
+FROM sys_roles
+WHERE code = 'manager';
+
+INSERT OR IGNORE INTO sys_role_scope_access (role_id, access_scope_id, access_mode)
+SELECT id, 'p919_daily_sales_summary', 'read'
+FROM sys_roles
+WHERE code IN ('operator', 'viewer');
@@ -0,0 +1,24 @@
+-- compat: expand
+-- Объявления о новых возможностях («Что нового»): пишет администратор, видят
+-- все пользователи в панели под кнопкой в шапке. Черновик — published_at IS NULL.
+CREATE TABLE IF NOT EXISTS sys_announcements (
+    id           TEXT PRIMARY KEY,
+    title        TEXT NOT NULL,
+    body         TEXT NOT NULL,
+    version      TEXT,                   -- версия релиза, к которой относится объявление
+    tab_key      TEXT,                   -- вкладка, которую открыть по клику
+    published_at TEXT,                   -- UTC ISO8601; NULL — черновик
+    created_by   TEXT,                   -- sys_users.id автора
+    created_at   TEXT NOT NULL,
+    updated_at   TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_announcements_published ON sys_announcements (published_at);
+
+-- Прочитанные пользователем объявления
+CREATE TABLE IF NOT EXISTS sys_announcement_reads (
+    user_id         TEXT NOT NULL,       -- sys_users.id
+    announcement_id TEXT NOT NULL,       -- sys_announcements.id
+    read_at         TEXT NOT NULL,
+    PRIMARY KEY (user_id, announcement_id)
+);
@@ -0,0 +1,13 @@
+-- compat: expand
+-- Ручные связи продажи WB (a012) со строками финансового отчёта (p903), когда
+-- SRID в отчёте не совпадает с документом. Ключ строки отчёта — rrd_id: он
+-- стабилен между перезагрузками p903, в отличие от id записи.
+CREATE TABLE IF NOT EXISTS a012_wb_sales_finance_links (
+    sale_id   TEXT    NOT NULL,          -- a012_wb_sales.id
+    rrd_id    INTEGER NOT NULL,          -- p903_wb_finance_report.rrd_id
+    linked_by TEXT,                      -- sys_users.id
+    linked_at TEXT    NOT NULL,          -- UTC ISO8601
+    PRIMARY KEY (sale_id, rrd_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_a012_wb_sales_finance_links_rrd ON a012_wb_sales_finance_links (rrd_id);
@@ -0,0 +1,7 @@
+-- compat: expand
+-- Журнал действий с документами (создан / изменён / проведён / распроведён / удалён)
+-- пишется в sys_audit_log: entity_type — scope агрегата ('a012_wb_sales'), entity_id —
+-- id документа, details — сводка изменённых полей. Вкладка «История» карточки
+-- читает записи по документу.
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity
+    ON sys_audit_log(entity_type, entity_id, created_at);
@@ -0,0 +1,15 @@
+-- compat: expand
+-- Печатные формы документов (претензионные письма, акты приёмки): HTML-шаблоны
+-- с полями {{...}}, заполняются данными документа и печатаются в PDF из браузера.
+CREATE TABLE IF NOT EXISTS sys_print_templates (
+    id          TEXT PRIMARY KEY,        -- UUID
+    entity_type TEXT NOT NULL,           -- агрегат: a012_wb_sales, a015_wb_orders
+    kind        TEXT NOT NULL,           -- claim_letter / acceptance_act / other
+    name        TEXT NOT NULL,
+    body_html   TEXT NOT NULL,
+    updated_at  TEXT NOT NULL,           -- UTC ISO8601
+    updated_by  TEXT NOT NULL            -- sys_users.username
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_print_templates_entity
+    ON sys_print_templates(entity_type, name);
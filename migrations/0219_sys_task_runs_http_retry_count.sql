@@ -0,0 +1,3 @@
+-- compat: expand
+-- Повторы запросов к API маркетплейсов (429/5xx/обрыв связи) за запуск задачи
+ALTER TABLE sys_task_runs ADD COLUMN http_retry_count INTEGER;
@@ -0,0 +1,20 @@
+-- compat: expand
+-- Расхождения схемы ответов маркетплейсов: новые и пропавшие поля, строки,
+-- не разобранные клиентом. Открытые записи — проблемы проверки качества schema_drift.
+CREATE TABLE IF NOT EXISTS sys_schema_drift (
+    id              TEXT PRIMARY KEY,        -- UUID
+    endpoint        TEXT NOT NULL,           -- WB /api/v1/supplier/sales
+    mapping         TEXT NOT NULL,           -- таблица сопоставления: a012_wb_sales
+    kind            TEXT NOT NULL,           -- unknown_field / missing_field / row_rejected
+    field           TEXT NOT NULL,           -- путь поля или текст ошибки разбора
+    example_value   TEXT,
+    occurrences     INTEGER NOT NULL DEFAULT 0,
+    first_seen_at   TEXT NOT NULL,           -- UTC ISO8601
+    last_seen_at    TEXT NOT NULL,
+    status          TEXT NOT NULL DEFAULT 'open', -- open / acknowledged
+    acknowledged_by TEXT,                    -- sys_users.username
+    acknowledged_at TEXT
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_sys_schema_drift_key
+    ON sys_schema_drift(endpoint, kind, field);
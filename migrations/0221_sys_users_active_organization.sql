@@ -0,0 +1,4 @@
+-- compat: expand
+-- Организация, данными которой ограничены списки и карточки документов пользователя
+-- (переключатель в шапке). NULL — все организации.
+ALTER TABLE sys_users ADD COLUMN active_organization_ref TEXT;
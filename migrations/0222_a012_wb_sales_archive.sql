@@ -0,0 +1,14 @@
+-- compat: expand
+-- Архив документов WB Sales (a012) по месяцам даты продажи — по той же схеме,
+-- что и архив проекций p900/p904 (см. 0184_projection_archive.sql и
+-- shared::data::projection_archive). Документы без sale_date остаются в рабочей таблице.
+--
+-- Набор и порядок колонок копируются из a012_wb_sales (UNION ALL через SELECT *),
+-- поэтому любая миграция, добавляющая колонку в a012_wb_sales, должна добавить её и в архив.
+CREATE TABLE IF NOT EXISTS a012_wb_sales_archive AS
+    SELECT * FROM a012_wb_sales WHERE 0;
+
+CREATE INDEX IF NOT EXISTS idx_a012_archive_id ON a012_wb_sales_archive(id);
+CREATE INDEX IF NOT EXISTS idx_a012_archive_sale_date ON a012_wb_sales_archive(sale_date);
+CREATE INDEX IF NOT EXISTS idx_a012_archive_document_no ON a012_wb_sales_archive(document_no);
+CREATE INDEX IF NOT EXISTS idx_a012_archive_sale_id ON a012_wb_sales_archive(sale_id);
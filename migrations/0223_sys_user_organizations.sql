@@ -0,0 +1,27 @@
+-- compat: expand
+-- Организации, данные которых доступны пользователю. Не-администратор может выбрать
+-- активной только организацию из этого списка; организация хоста без членства
+-- игнорируется. Назначает администратор в карточке пользователя.
+CREATE TABLE IF NOT EXISTS sys_user_organizations (
+    user_id         TEXT NOT NULL,             -- sys_users.id
+    organization_id TEXT NOT NULL,             -- a002_organization.id
+    created_at      TEXT NOT NULL,
+    created_by      TEXT,                      -- sys_users.id администратора
+    PRIMARY KEY (user_id, organization_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sys_user_organizations_org ON sys_user_organizations(organization_id);
+
+-- Перенос текущего состояния: выбранная пользователем организация остаётся доступной.
+INSERT OR IGNORE INTO sys_user_organizations (user_id, organization_id, created_at, created_by)
+SELECT id, active_organization_ref, datetime('now'), NULL
+FROM sys_users
+WHERE is_admin = 0 AND active_organization_ref IS NOT NULL AND active_organization_ref <> '';
+
+-- В базе с единственной организацией она доступна всем пользователям, как и раньше.
+INSERT OR IGNORE INTO sys_user_organizations (user_id, organization_id, created_at, created_by)
+SELECT u.id, o.id, datetime('now'), NULL
+FROM sys_users u, a002_organization o
+WHERE u.is_admin = 0
+  AND o.is_deleted = 0
+  AND (SELECT COUNT(*) FROM a002_organization WHERE is_deleted = 0) = 1;
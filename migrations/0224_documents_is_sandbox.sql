@@ -0,0 +1,13 @@
+-- compat: expand
+-- Признак тестовых данных: документ загружен через подключение в режиме песочницы
+-- (a006_connection_mp.test_mode). Такие документы не проводятся при импорте,
+-- пропускаются пакетным проведением за период и перепроведением за период.
+ALTER TABLE a010_ozon_fbs_posting ADD COLUMN is_sandbox INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE a011_ozon_fbo_posting ADD COLUMN is_sandbox INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE a012_wb_sales ADD COLUMN is_sandbox INTEGER NOT NULL DEFAULT 0;
+-- Архив повторяет набор и порядок колонок a012_wb_sales (см. 0222)
+ALTER TABLE a012_wb_sales_archive ADD COLUMN is_sandbox INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE a013_ym_order ADD COLUMN is_sandbox INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE a015_wb_orders ADD COLUMN is_sandbox INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE a016_ym_returns ADD COLUMN is_sandbox INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE a034_ym_realization ADD COLUMN is_sandbox INTEGER NOT NULL DEFAULT 0;